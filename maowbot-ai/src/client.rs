@@ -2,13 +2,16 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error};
 use uuid::Uuid;
 
 use crate::function::{Function, FunctionRegistry};
 use crate::memory::MemoryManager;
 use crate::provider::Provider;
-use crate::traits::{AiApi, ChatMessage, ChatResponse, ModelProvider};
+use crate::traits::{ChatMessage, ChatResponse, ModelProvider};
+
+/// Channel chat summaries given to the agent alongside the user's own history
+const CHANNEL_SUMMARIES_IN_CONTEXT: usize = 6;
 
 /// Represents a client for AI services
 pub struct AiClient {
@@ -157,6 +160,17 @@ impl AiClient {
         user_id: impl Into<String>,
         message: &str,
         context_size: usize,
+    ) -> anyhow::Result<String> {
+        self.agent_with_channel_memory(user_id, None, message, context_size).await
+    }
+
+    /// Like `agent_with_memory`, with the channel's summaries of earlier chat as context
+    pub async fn agent_with_channel_memory(
+        &self,
+        user_id: impl Into<String>,
+        channel: Option<&str>,
+        message: &str,
+        context_size: usize,
     ) -> anyhow::Result<String> {
         let user_id = user_id.into();
         
@@ -179,6 +193,17 @@ impl AiClient {
                 content: "You are a helpful AI assistant for MaowBot with access to functions. When appropriate, call functions to complete tasks for the user.".to_string(),
             });
         }
+
+        if let Some(channel) = channel {
+            let summaries = self.memory.channel_summaries(channel, CHANNEL_SUMMARIES_IN_CONTEXT).await?;
+            if !summaries.is_empty() {
+                let at = messages.iter().take_while(|msg| msg.role == "system").count();
+                messages.insert(at, ChatMessage {
+                    role: "system".to_string(),
+                    content: format!("Earlier in this channel's stream:\n{}", summaries.join("\n")),
+                });
+            }
+        }
         
         let provider = self.get_provider(None).await?;
         let functions = self.functions.get_all().await;
@@ -191,7 +216,7 @@ impl AiClient {
             debug!("Function call requested: {}", function_call.name);
             
             // Execute the function
-            let result = match self.functions.execute(&function_call.name, function_call.arguments).await {
+            match self.functions.execute(&function_call.name, function_call.arguments).await {
                 Ok(result) => {
                     let result_str = result.to_string();
                     
@@ -259,9 +284,7 @@ impl AiClient {
                     
                     error_response
                 }
-            };
-            
-            result
+            }
         } else {
             // If no function call, use the text response
             let text_response = response.content.unwrap_or_else(|| "I don't know how to respond.".to_string());
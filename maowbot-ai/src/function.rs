@@ -116,6 +116,12 @@ pub struct FunctionRegistry {
     functions: Arc<RwLock<HashMap<String, Function>>>,
 }
 
+impl Default for FunctionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FunctionRegistry {
     /// Create a new empty function registry
     pub fn new() -> Self {
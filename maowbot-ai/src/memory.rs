@@ -62,7 +62,7 @@ impl MemorySystem for InMemorySystem {
         
         // Get the latest entries up to limit
         let memory_count = user_memories.len();
-        let start_idx = if memory_count > limit { memory_count - limit } else { 0 };
+        let start_idx = memory_count.saturating_sub(limit);
         
         let messages = user_memories
             .iter()
@@ -108,6 +108,12 @@ pub struct MemoryManager {
     default_system: String,
 }
 
+impl Default for MemoryManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemoryManager {
     /// Create a new memory manager with an in-memory system as default
     pub fn new() -> Self {
@@ -163,4 +169,26 @@ impl MemoryManager {
         let system = self.get_system(None).await;
         system.retrieve(user_id, limit).await
     }
+
+    /// Store a summary of a stretch of a channel's chat
+    pub async fn store_channel_summary(&self, channel: &str, summary: &str) -> anyhow::Result<()> {
+        self.store_message(
+            &channel_memory_key(channel),
+            ChatMessage {
+                role: "system".to_string(),
+                content: summary.to_string(),
+            },
+        ).await
+    }
+
+    /// The latest chat summaries for a channel, oldest first
+    pub async fn channel_summaries(&self, channel: &str, limit: usize) -> anyhow::Result<Vec<String>> {
+        let messages = self.retrieve_messages(&channel_memory_key(channel), limit).await?;
+        Ok(messages.into_iter().map(|m| m.content).collect())
+    }
+}
+
+/// Memory key a channel's chat summaries are kept under, next to the per-user keys
+pub fn channel_memory_key(channel: &str) -> String {
+    format!("channel:{}", channel.trim_start_matches('#').to_lowercase())
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -6,16 +6,12 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 use chrono::Utc;
 use tracing::{debug, error, info, trace};
-use maowbot_common::error::Error as MaowError;
-use maowbot_common::models::analytics::BotEvent;
 use maowbot_common::models::platform::Platform;
 use maowbot_common::models::user::User;
 use maowbot_common::models::ai::{
-    AiProvider, AiCredential, AiModel, AiTrigger, AiMemory, 
-    AiTriggerWithDetails, AiAgent, AiAction, AiSystemPrompt, 
+    AiProvider, AiCredential, AiModel, AiTrigger, AiMemory, AiAgent, AiAction, AiSystemPrompt, 
     AiAgentWithDetails, TriggerType, MemoryRole, ActionHandlerType
 };
-use crate::traits::{AiApi, ChatResponse};
 use maowbot_common::traits::repository_traits::{
     CredentialsRepository, UserRepo, AiProviderRepository, AiCredentialRepository,
     AiModelRepository, AiTriggerRepository, AiMemoryRepository, AiConfigurationRepository,
@@ -29,6 +25,9 @@ use crate::provider::{Provider, OpenAIProvider, AnthropicProvider};
 use crate::models::ProviderConfig;
 use crate::traits::ChatMessage;
 
+/// Discord user mentions like <@123456> or <@!123456>
+static MENTION: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"<@!?\d+>").unwrap());
+
 /// AI service for integrating with MaowBot core
 pub struct AiService {
     /// The AI client
@@ -38,6 +37,7 @@ pub struct AiService {
     /// User repository for looking up users
     user_repo: Arc<dyn UserRepo + Send + Sync>,
     /// Credentials repository
+    #[allow(dead_code)]
     cred_repo: Arc<dyn CredentialsRepository + Send + Sync>,
     
     // AI Repositories
@@ -380,7 +380,7 @@ impl AiService {
         tracing::info!("Getting current provider configuration");
         
         // If we have repositories, try to get the configuration from the database
-        if let (Some(config_repo), Some(provider_repo), Some(cred_repo), Some(model_repo)) = 
+        if let (Some(config_repo), Some(_provider_repo), Some(_cred_repo), Some(_model_repo)) = 
             (&self.config_repo, &self.provider_repo, &self.ai_credential_repo, &self.model_repo) {
             
             // Try to get the default configuration
@@ -583,12 +583,8 @@ impl AiService {
                                 
                                 // Also check if the message starts with the prefix with a mention
                                 if normalized_message.contains(&prefix) {
-                                    let mention_pattern = r"<@!?\d+>";
-                                    let re = regex::Regex::new(mention_pattern)
-                                        .unwrap_or_else(|_| regex::Regex::new("never match").unwrap());
-                                    
-                                    if re.is_match(&normalized_message) {
-                                        let without_mentions = re.replace_all(&normalized_message, "").trim().to_string();
+                                    if MENTION.is_match(&normalized_message) {
+                                        let without_mentions = MENTION.replace_all(&normalized_message, "").trim().to_string();
                                         if without_mentions.starts_with(&prefix) {
                                             trace!("🔍 AI SERVICE: Prefix trigger matched after removing mentions: '{}'", trigger.pattern);
                                             return true;
@@ -610,12 +606,10 @@ impl AiService {
                                     }
                                 }
                             },
-                            "mention" => {
-                                // Look for mention patterns like <@123456> or @username
-                                if normalized_message.contains("<@") || normalized_message.contains("@maow") {
-                                    trace!("🔍 AI SERVICE: Mention trigger matched");
-                                    return true;
-                                }
+                            // Look for mention patterns like <@123456> or @username
+                            "mention" if normalized_message.contains("<@") || normalized_message.contains("@maow") => {
+                                trace!("🔍 AI SERVICE: Mention trigger matched");
+                                return true;
                             },
                             _ => {
                                 // Other trigger types not implemented yet
@@ -764,7 +758,7 @@ impl AiService {
         
         // Attempt to process with AI
         info!("🔍 AI SERVICE: Calling agent_with_memory");
-        let result = match self.client.agent_with_memory(user_id.to_string(), message, 10).await {
+        match self.client.agent_with_memory(user_id.to_string(), message, 10).await {
             Ok(response) => {
                 info!("🔍 AI SERVICE: Successfully generated response: '{}'", response);
                 
@@ -792,9 +786,7 @@ impl AiService {
                 error!("🔍 AI SERVICE: Failed to generate response: {:?}", e);
                 Err(e)
             }
-        };
-        
-        result
+        }
     }
     
     /// Register a function by name and description 
@@ -852,7 +844,7 @@ impl AiService {
     pub async fn process_chat_message(
         &self,
         _platform: Platform,
-        _channel: &str,
+        channel: &str,
         user: &User,
         message: &str,
     ) -> anyhow::Result<Option<String>> {
@@ -898,7 +890,7 @@ impl AiService {
         }
         
         // Get response from AI
-        match self.client.agent_with_memory(user.user_id.to_string(), &processed_message, 10).await {
+        match self.client.agent_with_channel_memory(user.user_id.to_string(), Some(channel), &processed_message, 10).await {
             Ok(response) => {
                 debug!("AI response: {}", response);
                 Ok(Some(response))
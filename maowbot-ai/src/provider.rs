@@ -679,6 +679,12 @@ pub struct Provider {
     providers: Arc<RwLock<HashMap<String, Arc<dyn ModelProvider>>>>,
 }
 
+impl Default for Provider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Provider {
     /// Create a new provider factory
     pub fn new() -> Self {
@@ -1,16 +1,28 @@
 use std::collections::VecDeque;
 
+pub use maowbot_proto::plugs::{ChatBadge, ChatEmoteRange, ChatReplyParent};
+
 #[derive(Clone)]
 pub struct ChatMessage {
     pub author: String,
     pub text: String,
+    pub badges: Vec<ChatBadge>,
+    /// Name color as "#RRGGBB"
+    pub color: Option<String>,
+    pub reply_to: Option<ChatReplyParent>,
+    /// Emote positions in `text`, in order
+    pub emotes: Vec<ChatEmoteRange>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct ChatEvent {
     pub channel: String,
     pub author: String,
     pub body: String,
+    pub badges: Vec<ChatBadge>,
+    pub color: Option<String>,
+    pub reply_to: Option<ChatReplyParent>,
+    pub emotes: Vec<ChatEmoteRange>,
 }
 
 pub struct ChatState {
@@ -18,6 +30,12 @@ pub struct ChatState {
     max_messages: usize,
 }
 
+impl Default for ChatState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ChatState {
     pub fn new() -> Self {
         Self {
@@ -30,6 +48,10 @@ impl ChatState {
         let msg = ChatMessage {
             author: event.author,
             text: event.body,
+            badges: event.badges,
+            color: event.color,
+            reply_to: event.reply_to,
+            emotes: event.emotes,
         };
 
         self.messages.push_back(msg);
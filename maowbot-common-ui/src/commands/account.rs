@@ -3,8 +3,10 @@ use super::CommandError;
 use maowbot_proto::maowbot::services::{
     BeginAuthFlowRequest, ListCredentialsRequest, RefreshCredentialRequest, 
     RevokeCredentialRequest, StoreCredentialRequest, PlatformUserIdentifier,
+    GetScopeStatusRequest, GetScopeStatusResponse,
+    ChatIdentity, ListChatIdentitiesRequest, SetChatIdentityRequest,
 };
-use maowbot_proto::maowbot::common::Platform;
+use maowbot_proto::maowbot::common::{Platform, PlatformCredential};
 
 /// Convert Platform enum to user-friendly string
 fn platform_to_display_string(platform: Platform) -> &'static str {
@@ -16,6 +18,7 @@ fn platform_to_display_string(platform: Platform) -> &'static str {
         Platform::Vrchat => "VRChat",
         Platform::VrchatPipeline => "VRChatPipeline",
         Platform::Obs => "OBS",
+        Platform::Kick => "Kick",
         Platform::Unknown => "Unknown",
     }
 }
@@ -80,8 +83,8 @@ impl AccountCommands {
         platform_str: &str,
         typed_name: &str,
         is_bot: bool,
-        is_broadcaster: bool,
-        is_teammate: bool,
+        _is_broadcaster: bool,
+        _is_teammate: bool,
     ) -> Result<AddAccountResult, CommandError> {
         // Parse platform
         let platform = parse_platform(platform_str)?;
@@ -92,6 +95,7 @@ impl AccountCommands {
             is_bot,
             redirect_uri: "http://127.0.0.1:9876".to_string(),
             requested_scopes: vec![],
+            device_code: false,
         };
         
         let mut client = client.credential.clone();
@@ -307,12 +311,9 @@ impl AccountCommands {
         platform_str: &str,
         user_str: &str,
         is_bot: bool,
-        _is_broadcaster: bool,
-        _is_teammate: bool,
+        is_broadcaster: bool,
+        is_teammate: bool,
     ) -> Result<SetAccountTypeResult, CommandError> {
-        // Note: The current gRPC API only supports updating is_bot flag
-        // is_broadcaster and is_teammate are not available in the proto definition
-        
         let platform = parse_platform(platform_str)?;
         
         // First, get the credential
@@ -349,6 +350,8 @@ impl AccountCommands {
         if let Some(info) = credential_info {
             let mut cred = info.credential.unwrap_or_default();
             cred.is_bot = is_bot;
+            cred.is_broadcaster = is_broadcaster;
+            cred.is_teammate = is_teammate;
             
             let store_request = StoreCredentialRequest {
                 credential: Some(cred),
@@ -362,7 +365,10 @@ impl AccountCommands {
                 .map_err(|e| CommandError::GrpcError(e.to_string()))?;
                 
             Ok(SetAccountTypeResult {
-                message: format!("Updated credential: is_bot={}", is_bot),
+                message: format!(
+                    "Updated credential: is_bot={} is_broadcaster={} is_teammate={}",
+                    is_bot, is_broadcaster, is_teammate
+                ),
             })
         } else {
             Err(CommandError::NotFound(format!(
@@ -371,6 +377,86 @@ impl AccountCommands {
             )))
         }
     }
+
+    /// Find a credential by user_id, global username or platform login name
+    pub async fn find_credential(
+        client: &GrpcClient,
+        platform_str: &str,
+        user_str: &str,
+    ) -> Result<PlatformCredential, CommandError> {
+        let platform = parse_platform(platform_str)?;
+        let request = ListCredentialsRequest {
+            platforms: vec![platform as i32],
+            active_only: false,
+            include_expired: true,
+            page: None,
+        };
+
+        let mut client = client.credential.clone();
+        let response = client
+            .list_credentials(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        response.into_inner().credentials.into_iter()
+            .find(|info| {
+                let by_user = info.user.as_ref().is_some_and(|u| u.global_username.eq_ignore_ascii_case(user_str));
+                let by_cred = info.credential.as_ref().is_some_and(|c| {
+                    c.user_id == user_str || c.user_name.eq_ignore_ascii_case(user_str)
+                });
+                by_user || by_cred
+            })
+            .and_then(|info| info.credential)
+            .ok_or_else(|| CommandError::NotFound(format!(
+                "No credential found for platform={}, user={}",
+                platform_str, user_str
+            )))
+    }
+
+    /// Missing Twitch scopes and degraded features; `recheck` asks Twitch again
+    pub async fn scope_status(
+        client: &GrpcClient,
+        recheck: bool,
+    ) -> Result<GetScopeStatusResponse, CommandError> {
+        let mut client = client.credential.clone();
+        let response = client
+            .get_scope_status(GetScopeStatusRequest { recheck })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Per-channel identity assignments, for one platform or all (empty)
+    pub async fn list_chat_identities(
+        client: &GrpcClient,
+        platform: &str,
+    ) -> Result<Vec<ChatIdentity>, CommandError> {
+        let mut client = client.credential.clone();
+        let response = client
+            .list_chat_identities(ListChatIdentitiesRequest { platform: platform.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().identities)
+    }
+
+    /// Has `account` speak in `channel` ("*" for the platform default); None clears it
+    pub async fn set_chat_identity(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+        account: Option<&str>,
+    ) -> Result<ChatIdentity, CommandError> {
+        let mut client = client.credential.clone();
+        let response = client
+            .set_chat_identity(SetChatIdentityRequest {
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+                account: account.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
 }
 
 fn parse_platform(platform_str: &str) -> Result<Platform, CommandError> {
@@ -381,6 +467,7 @@ fn parse_platform(platform_str: &str) -> Result<Platform, CommandError> {
         "discord" => Ok(Platform::Discord),
         "vrchat" => Ok(Platform::Vrchat),
         "vrchat-pipeline" | "vrchatpipeline" => Ok(Platform::VrchatPipeline),
+        "kick" => Ok(Platform::Kick),
         _ => Err(CommandError::InvalidInput(format!("Unknown platform '{}'", platform_str))),
     }
 }
\ No newline at end of file
@@ -3,7 +3,6 @@ use anyhow::Result;
 use maowbot_proto::maowbot::services::*;
 use maowbot_proto::prost_types;
 use crate::grpc_client::GrpcClient;
-use serde_json::json;
 
 /// AI command handler for common UI functionality
 pub struct AiCommands;
@@ -145,14 +144,13 @@ impl AiCommands {
     
     /// Test a chat message
     pub async fn chat(client: &mut GrpcClient, message: String) -> Result<String> {
-        let mut messages = Vec::new();
-        messages.push(ChatMessage {
+        let messages = vec![ChatMessage {
             role: ChatRole::User as i32,
             content: message,
             name: String::new(),
             function_calls: vec![],
             metadata: HashMap::new(),
-        });
+        }];
         
         let response = client.ai
             .generate_chat(GenerateChatRequest {
@@ -0,0 +1,72 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    AlertRule, RaisedAlert, ListAlertRulesRequest, AddAlertRuleRequest, UpdateAlertRuleRequest,
+    DeleteAlertRuleRequest, ListRecentAlertsRequest, TestAlertRequest,
+};
+
+/// Ops alert rule command handlers
+pub struct AlertingCommands;
+
+impl AlertingCommands {
+    pub async fn list_rules(client: &GrpcClient) -> Result<Vec<AlertRule>, CommandError> {
+        let mut alerting_client = client.alerting.clone();
+        let response = alerting_client
+            .list_alert_rules(ListAlertRulesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().rules)
+    }
+
+    pub async fn add_rule(client: &GrpcClient, request: AddAlertRuleRequest) -> Result<AlertRule, CommandError> {
+        let mut alerting_client = client.alerting.clone();
+        alerting_client
+            .add_alert_rule(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .rule
+            .ok_or_else(|| CommandError::DataError("Server returned no rule".to_string()))
+    }
+
+    /// Change a rule; unset fields stay as they are
+    pub async fn update_rule(client: &GrpcClient, request: UpdateAlertRuleRequest) -> Result<AlertRule, CommandError> {
+        let mut alerting_client = client.alerting.clone();
+        alerting_client
+            .update_alert_rule(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .rule
+            .ok_or_else(|| CommandError::DataError("Server returned no rule".to_string()))
+    }
+
+    pub async fn delete_rule(client: &GrpcClient, name: &str) -> Result<(), CommandError> {
+        let mut alerting_client = client.alerting.clone();
+        alerting_client
+            .delete_alert_rule(DeleteAlertRuleRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Alerts raised since the server started, newest first
+    pub async fn recent_alerts(client: &GrpcClient, limit: u32) -> Result<Vec<RaisedAlert>, CommandError> {
+        let mut alerting_client = client.alerting.clone();
+        let response = alerting_client
+            .list_recent_alerts(ListRecentAlertsRequest { limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().alerts)
+    }
+
+    /// Raise a made-up alert, skipping dedupe and quiet hours
+    pub async fn test_alert(client: &GrpcClient, kind: &str, severity: &str) -> Result<RaisedAlert, CommandError> {
+        let mut alerting_client = client.alerting.clone();
+        let response = alerting_client
+            .test_alert(TestAlertRequest { kind: kind.to_string(), severity: severity.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+}
@@ -0,0 +1,45 @@
+use crate::GrpcClient;
+use super::CommandError;
+use super::token::AuditInfo;
+use maowbot_proto::maowbot::services::ListAuditLogRequest;
+
+/// Which audit records to fetch. Text filters match anywhere, ignoring case.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub limit: i32,
+    pub method: String,
+    pub client_name: String,
+    pub target: String,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Leave out denied calls and plain access records
+    pub changes_only: bool,
+}
+
+/// Audit log query handlers
+pub struct AuditCommands;
+
+impl AuditCommands {
+    /// Most recent matching records first
+    pub async fn list(
+        client: &GrpcClient,
+        query: &AuditQuery,
+    ) -> Result<Vec<AuditInfo>, CommandError> {
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .list_audit_log(ListAuditLogRequest {
+                limit: query.limit,
+                method: query.method.clone(),
+                client_name: query.client_name.clone(),
+                target: query.target.clone(),
+                since: query.since.map(|t| maowbot_proto::prost_types::Timestamp {
+                    seconds: t.timestamp(),
+                    nanos: t.timestamp_subsec_nanos() as i32,
+                }),
+                changes_only: query.changes_only,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(response.into_inner().entries.into_iter().map(AuditInfo::from).collect())
+    }
+}
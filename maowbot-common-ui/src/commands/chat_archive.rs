@@ -0,0 +1,231 @@
+use crate::GrpcClient;
+use super::{CommandError, to_datetime, to_timestamp};
+use maowbot_proto::maowbot::services::{
+    ArchivedChatMessage, SearchMessagesRequest, GetLastSeenRequest, ListRetentionPoliciesRequest,
+    SetRetentionPolicyRequest, DeleteRetentionPolicyRequest, ChatActivityBucket, GetChatActivityRequest,
+};
+
+/// One archived chat message
+pub struct ArchivedMessage {
+    pub platform: String,
+    pub channel: String,
+    pub user_id: String,
+    pub username: String,
+    pub text: String,
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<ArchivedChatMessage> for ArchivedMessage {
+    fn from(m: ArchivedChatMessage) -> Self {
+        Self {
+            platform: m.platform,
+            channel: m.channel,
+            user_id: m.user_id,
+            username: m.username,
+            text: m.text,
+            timestamp: to_datetime(m.timestamp),
+        }
+    }
+}
+
+/// Filters for `search_messages`; empty strings and `None` match everything
+#[derive(Default)]
+pub struct ChatSearchFilter {
+    /// User UUID or global username
+    pub user: String,
+    pub platform: String,
+    pub channel: String,
+    pub text: String,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: i32,
+    pub offset: i32,
+}
+
+/// A page of search results
+pub struct ChatSearchResult {
+    pub messages: Vec<ArchivedMessage>,
+    pub total_count: i64,
+}
+
+/// Per-channel chat retention
+pub struct RetentionInfo {
+    pub platform: String,
+    pub channel: String,
+    pub retention_days: i32,
+}
+
+pub struct RetentionPolicies {
+    pub policies: Vec<RetentionInfo>,
+    pub default_retention_days: i32,
+}
+
+/// Message count of one channel in one bucket
+pub struct ActivityBucket {
+    pub platform: String,
+    pub channel: String,
+    pub bucket_start: Option<chrono::DateTime<chrono::Utc>>,
+    pub message_count: i32,
+}
+
+impl From<ChatActivityBucket> for ActivityBucket {
+    fn from(b: ChatActivityBucket) -> Self {
+        Self {
+            platform: b.platform,
+            channel: b.channel,
+            bucket_start: to_datetime(b.bucket_start),
+            message_count: b.message_count,
+        }
+    }
+}
+
+/// Chat activity over a time range
+pub struct ChatActivity {
+    pub buckets: Vec<ActivityBucket>,
+    /// Busiest buckets first
+    pub peaks: Vec<ActivityBucket>,
+    pub bucket_seconds: i32,
+}
+
+/// Chat archive command handlers
+pub struct ChatArchiveCommands;
+
+impl ChatArchiveCommands {
+    /// Search stored chat messages, newest first
+    pub async fn search_messages(
+        client: &GrpcClient,
+        filter: ChatSearchFilter,
+    ) -> Result<ChatSearchResult, CommandError> {
+        let request = SearchMessagesRequest {
+            user: filter.user,
+            platform: filter.platform,
+            channel: filter.channel,
+            text: filter.text,
+            since: filter.since.map(to_timestamp),
+            until: filter.until.map(to_timestamp),
+            limit: filter.limit,
+            offset: filter.offset,
+        };
+
+        let mut archive_client = client.chat_archive.clone();
+        let response = archive_client
+            .search_messages(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(ChatSearchResult {
+            messages: response.messages.into_iter().map(ArchivedMessage::from).collect(),
+            total_count: response.total_count,
+        })
+    }
+
+    /// The most recent message from `user`, optionally limited to one channel
+    pub async fn last_seen(
+        client: &GrpcClient,
+        user: &str,
+        channel: Option<&str>,
+    ) -> Result<Option<ArchivedMessage>, CommandError> {
+        let request = GetLastSeenRequest {
+            user: user.to_string(),
+            platform: String::new(),
+            channel: channel.unwrap_or_default().to_string(),
+        };
+
+        let mut archive_client = client.chat_archive.clone();
+        let response = archive_client
+            .get_last_seen(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(response.message.filter(|_| response.found).map(ArchivedMessage::from))
+    }
+
+    pub async fn list_retention(
+        client: &GrpcClient,
+    ) -> Result<RetentionPolicies, CommandError> {
+        let mut archive_client = client.chat_archive.clone();
+        let response = archive_client
+            .list_retention_policies(ListRetentionPoliciesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(RetentionPolicies {
+            policies: response.policies.into_iter().map(|p| RetentionInfo {
+                platform: p.platform,
+                channel: p.channel,
+                retention_days: p.retention_days,
+            }).collect(),
+            default_retention_days: response.default_retention_days,
+        })
+    }
+
+    /// Keep messages from one channel for `retention_days`
+    pub async fn set_retention(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+        retention_days: i32,
+    ) -> Result<(), CommandError> {
+        let mut archive_client = client.chat_archive.clone();
+        archive_client
+            .set_retention_policy(SetRetentionPolicyRequest {
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+                retention_days,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Per-bucket message counts between `since` and `until` (server defaults: the last 24 hours)
+    pub async fn activity(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        until: Option<chrono::DateTime<chrono::Utc>>,
+        peak_count: i32,
+    ) -> Result<ChatActivity, CommandError> {
+        let request = GetChatActivityRequest {
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+            since: since.map(to_timestamp),
+            until: until.map(to_timestamp),
+            peak_count,
+        };
+
+        let mut archive_client = client.chat_archive.clone();
+        let response = archive_client
+            .get_chat_activity(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(ChatActivity {
+            buckets: response.buckets.into_iter().map(ActivityBucket::from).collect(),
+            peaks: response.peaks.into_iter().map(ActivityBucket::from).collect(),
+            bucket_seconds: response.bucket_seconds,
+        })
+    }
+
+    /// Return a channel to the default retention
+    pub async fn delete_retention(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+    ) -> Result<(), CommandError> {
+        let mut archive_client = client.chat_archive.clone();
+        archive_client
+            .delete_retention_policy(DeleteRetentionPolicyRequest {
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+}
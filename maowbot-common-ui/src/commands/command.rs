@@ -1,11 +1,12 @@
 use crate::{GrpcClient, CommandResult, CommandError};
+use super::MAX_PAGE_SIZE;
 use maowbot_proto::maowbot::services::{
     CreateCommandRequest, GetCommandRequest, UpdateCommandRequest,
     DeleteCommandRequest, ListCommandsRequest, ExecuteCommandRequest,
-    GetCommandUsageRequest, CommandInfo,
+    GetCommandUsageRequest, GetCommandStatsRequest, GetCommandStatsResponse, CommandInfo,
+    ImportCommandsRequest, ImportCommandsResponse,
 };
 use maowbot_proto::maowbot::common::{Command, PageRequest};
-use uuid::Uuid;
 
 // Result structures
 pub struct CreateCommandResult {
@@ -23,6 +24,8 @@ pub struct UpdateCommandResult {
 pub struct ListCommandsResult {
     pub commands: Vec<CommandInfo>,
     pub total_count: i32,
+    /// Empty on the last page
+    pub next_page_token: String,
 }
 
 pub struct ExecuteCommandResult {
@@ -54,7 +57,7 @@ impl CommandCommands {
             metadata.insert("plugin_id".to_string(), pid.to_string());
         }
         if cooldown_warnonce {
-            metadata.insert("cooldown_warnonce".to_string(), "true".to_string());
+            metadata.insert("cooldown_warn_once".to_string(), "true".to_string());
         }
         if let Some(cred) = respond_with_credential {
             metadata.insert("respond_with_credential".to_string(), cred.to_string());
@@ -168,6 +171,32 @@ impl CommandCommands {
         platform: Option<&str>,
         active_only: bool,
         page_size: i32,
+    ) -> Result<CommandResult<ListCommandsResult>, CommandError> {
+        Self::list_commands_page(client, platform, active_only, page_size, String::new()).await
+    }
+
+    /// Every command on the platform, following page tokens to the end.
+    pub async fn list_all_commands(
+        client: &GrpcClient,
+        platform: Option<&str>,
+        active_only: bool,
+    ) -> Result<CommandResult<ListCommandsResult>, CommandError> {
+        let mut all = Self::list_commands_page(client, platform, active_only, MAX_PAGE_SIZE, String::new()).await?;
+        while !all.data.next_page_token.is_empty() {
+            let token = std::mem::take(&mut all.data.next_page_token);
+            let page = Self::list_commands_page(client, platform, active_only, MAX_PAGE_SIZE, token).await?;
+            all.data.commands.extend(page.data.commands);
+            all.data.next_page_token = page.data.next_page_token;
+        }
+        Ok(all)
+    }
+
+    async fn list_commands_page(
+        client: &GrpcClient,
+        platform: Option<&str>,
+        active_only: bool,
+        page_size: i32,
+        page_token: String,
     ) -> Result<CommandResult<ListCommandsResult>, CommandError> {
         let request = ListCommandsRequest {
             platform: platform.unwrap_or_default().to_string(),
@@ -175,8 +204,11 @@ impl CommandCommands {
             name_prefix: String::new(),
             page: Some(PageRequest {
                 page_size,
-                page_token: String::new(),
+                page_token,
             }),
+            order_by: String::new(),
+            descending: false,
+            read_mask: None,
         };
 
         let response = client.command.clone()
@@ -188,11 +220,13 @@ impl CommandCommands {
         let total_count = resp.page.as_ref()
             .map(|p| p.total_count)
             .unwrap_or(resp.commands.len() as i32);
+        let next_page_token = resp.page.map(|p| p.next_page_token).unwrap_or_default();
 
         Ok(CommandResult {
             data: ListCommandsResult {
                 commands: resp.commands,
                 total_count,
+                next_page_token,
             },
             warnings: vec![],
         })
@@ -262,6 +296,61 @@ impl CommandCommands {
         })
     }
 
+    /// Uses, failures and handler latency of one command over the last `days` days.
+    pub async fn get_command_stats(
+        client: &GrpcClient,
+        platform: &str,
+        command_name: &str,
+        days: i64,
+    ) -> Result<CommandResult<GetCommandStatsResponse>, CommandError> {
+        let cmd = Self::find_command_by_name(client, platform, command_name).await?
+            .ok_or_else(|| CommandError::NotFound(format!("Command '{}' not found on platform '{}'", command_name, platform)))?;
+        let since = chrono::Utc::now() - chrono::Duration::days(days.max(1));
+        let request = GetCommandStatsRequest {
+            command_id: cmd.command_id,
+            since: Some(maowbot_proto::prost_types::Timestamp {
+                seconds: since.timestamp(),
+                nanos: 0,
+            }),
+        };
+
+        let response = client.command.clone()
+            .get_command_stats(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: response.into_inner(),
+            warnings: vec![],
+        })
+    }
+
+    /// Imports another bot's exported commands (`source` is "nightbot",
+    /// "streamelements" or "fossabot"); with `dry_run` only the report comes back.
+    pub async fn import_commands(
+        client: &GrpcClient,
+        source: &str,
+        content: String,
+        platform: &str,
+        dry_run: bool,
+    ) -> Result<CommandResult<ImportCommandsResponse>, CommandError> {
+        let request = ImportCommandsRequest {
+            source: source.to_string(),
+            content,
+            platform: platform.to_string(),
+            dry_run,
+        };
+
+        let response = client.command.clone()
+            .import_commands(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: response.into_inner(),
+            warnings: vec![],
+        })
+    }
 
     // Helper method to find command by name and platform
     pub async fn find_command_by_name(
@@ -300,7 +389,25 @@ impl CommandCommands {
     ) -> Result<CommandResult<UpdateCommandResult>, CommandError> {
         if let Some(mut cmd) = Self::find_command_by_name(client, platform, command_name).await? {
             let command_id = cmd.command_id.clone();
-            cmd.metadata.insert("cooldown_warnonce".to_string(), cooldown_warnonce.to_string());
+            cmd.metadata.insert("cooldown_warn_once".to_string(), cooldown_warnonce.to_string());
+            Self::update_command(client, &command_id, cmd).await
+        } else {
+            Err(CommandError::DataError(format!("Command '{}' not found on platform '{}'", command_name, platform)))
+        }
+    }
+
+    /// Sets one metadata-backed field, e.g. "user_cooldown_seconds",
+    /// "role_cooldowns" or "cooldown_bypass_mods".
+    pub async fn update_metadata(
+        client: &GrpcClient,
+        platform: &str,
+        command_name: &str,
+        key: &str,
+        value: String,
+    ) -> Result<CommandResult<UpdateCommandResult>, CommandError> {
+        if let Some(mut cmd) = Self::find_command_by_name(client, platform, command_name).await? {
+            let command_id = cmd.command_id.clone();
+            cmd.metadata.insert(key.to_string(), value);
             Self::update_command(client, &command_id, cmd).await
         } else {
             Err(CommandError::DataError(format!("Command '{}' not found on platform '{}'", command_name, platform)))
@@ -1,8 +1,14 @@
 use crate::GrpcClient;
 use super::CommandError;
 use maowbot_proto::maowbot::services::{
-    GetConfigRequest, SetConfigRequest, DeleteConfigRequest, ListConfigsRequest,
-    ShutdownServerRequest,
+    GetConfigRequest, SetConfigRequest, DeleteConfigRequest, ListConfigsRequest, ConfigMetadata,
+    ShutdownServerRequest, RotateEncryptionKeyRequest, ExportBackupRequest, ImportBackupRequest,
+    BackupCounts, GetLogLevelsRequest, SetLogLevelRequest, GetSettingsSchemaRequest, ConfigType,
+    GetUpdateStatusRequest, CheckForUpdateRequest, ApplyUpdateRequest, UpdateStatus,
+    GetDatabaseStatusRequest, CreateDatabaseSnapshotRequest, CompactDatabaseRequest,
+    CheckDatabaseIntegrityRequest, DatabaseSnapshot, DatabaseIntegrityReport,
+    GetCertificateInfoRequest, RegenerateCertificateRequest, IssueClientCertificateRequest,
+    CertificateInfo as CertificateInfoProto, RunRetentionRequest, RetentionReport,
 };
 
 /// Result of listing configs
@@ -36,6 +42,141 @@ pub struct ShutdownResult {
     pub shutdown_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Result of an encryption key rotation
+pub struct RotateKeyResult {
+    pub message: String,
+    pub key_source: String,
+    /// (table.column, values re-encrypted)
+    pub columns: Vec<(String, u64)>,
+    pub total: u64,
+}
+
+/// Result of a full backup export
+pub struct ExportBackupResult {
+    /// JSON archive to write to disk
+    pub archive: Vec<u8>,
+    /// Needed to restore credentials; the server does not keep it
+    pub backup_key: String,
+    pub schema_version: i64,
+    pub counts: BackupCounts,
+}
+
+/// Result of restoring a full backup
+pub struct ImportBackupResult {
+    pub restored: BackupCounts,
+    pub skipped: BackupCounts,
+    pub warnings: Vec<String>,
+    pub schema_version: i64,
+}
+
+/// Current server log filter
+pub struct LogLevelsResult {
+    /// "text" or "json"
+    pub format: String,
+    pub base_filter: String,
+    /// (module, level) overrides set at runtime
+    pub overrides: Vec<(String, String)>,
+    pub effective_filter: String,
+}
+
+/// One known setting from the server's schema
+pub struct SettingSchemaInfo {
+    pub key: String,
+    pub category: String,
+    pub description: String,
+    /// "string", "integer", "float", "boolean" or "json"
+    pub value_type: String,
+    pub default_value: Option<String>,
+    pub allowed_values: Vec<String>,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub requires_restart: bool,
+    pub current_value: String,
+    pub is_set: bool,
+}
+
+/// Server self-update state
+pub struct UpdateStatusInfo {
+    /// idle, checking, up-to-date, available, downloading, staged or failed
+    pub phase: String,
+    pub current_version: String,
+    pub latest_version: Option<String>,
+    pub release_notes: String,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_checked: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_error: Option<String>,
+    pub update_available: bool,
+    pub auto_update: bool,
+    pub feed_url: String,
+    pub has_public_key: bool,
+    pub target: String,
+}
+
+/// Result of installing an update
+pub struct ApplyUpdateResult {
+    pub installed: bool,
+    pub version: String,
+    pub message: String,
+    pub restart_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// One pg_dump snapshot in postgres/snapshots
+pub struct SnapshotInfo {
+    pub name: String,
+    pub size_bytes: i64,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Result of a database integrity check
+pub struct IntegrityInfo {
+    pub ok: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Database size, snapshots and health
+pub struct DatabaseStatusResult {
+    pub server_version: String,
+    pub embedded: bool,
+    pub database_bytes: i64,
+    pub wal_bytes: i64,
+    /// Newest first
+    pub snapshots: Vec<SnapshotInfo>,
+    pub integrity: Option<IntegrityInfo>,
+    pub last_compacted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Result of a checkpoint + VACUUM run
+pub struct CompactResult {
+    pub database_bytes_before: i64,
+    pub database_bytes_after: i64,
+    pub wal_bytes_before: i64,
+    pub wal_bytes_after: i64,
+    pub log_rotated: bool,
+    pub duration_ms: i64,
+    pub warnings: Vec<String>,
+}
+
+/// The gRPC server certificate and mutual TLS state
+pub struct CertificateInfo {
+    pub sans: Vec<String>,
+    pub generated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub fingerprint: String,
+    /// false for a legacy self-signed certificate
+    pub has_ca: bool,
+    pub require_client_cert: bool,
+    pub restart_required: bool,
+}
+
+/// A client certificate issued by the server's CA
+pub struct ClientCertificate {
+    pub cert_pem: String,
+    pub key_pem: String,
+    pub ca_pem: String,
+    pub fingerprint: String,
+}
+
 /// Config command handlers
 pub struct ConfigCommands;
 
@@ -129,6 +270,32 @@ impl ConfigCommands {
         })
     }
     
+    /// Store a secret (e.g. a webhook token for `{secret:<key>}`); the server
+    /// keeps it encrypted and never lists it
+    pub async fn set_secret(
+        client: &GrpcClient,
+        key: &str,
+        value: &str,
+    ) -> Result<(), CommandError> {
+        let request = SetConfigRequest {
+            key: key.to_string(),
+            value: value.to_string(),
+            metadata: Some(ConfigMetadata {
+                is_secret: true,
+                ..Default::default()
+            }),
+            validate_only: false,
+        };
+
+        let mut client = client.config.clone();
+        client
+            .set_config(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(())
+    }
+
     /// Delete a configuration key
     pub async fn delete_config(
         client: &GrpcClient,
@@ -176,4 +343,393 @@ impl ConfigCommands {
             shutdown_at,
         })
     }
-}
\ No newline at end of file
+    
+    /// Show the server's log format and per-module levels
+    pub async fn get_log_levels(
+        client: &GrpcClient,
+    ) -> Result<LogLevelsResult, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .get_log_levels(GetLogLevelsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(LogLevelsResult {
+            format: response.format,
+            base_filter: response.base_filter,
+            overrides: response.overrides.into_iter().map(|o| (o.module, o.level)).collect(),
+            effective_filter: response.effective_filter,
+        })
+    }
+
+    /// Change one module's log level at runtime; `None` removes the override
+    pub async fn set_log_level(
+        client: &GrpcClient,
+        module: &str,
+        level: Option<&str>,
+    ) -> Result<LogLevelsResult, CommandError> {
+        if module.trim().is_empty() {
+            return Err(CommandError::InvalidInput("Module path cannot be empty".to_string()));
+        }
+        let request = SetLogLevelRequest {
+            module: module.to_string(),
+            level: level.unwrap_or("").to_string(),
+        };
+
+        let mut config_client = client.config.clone();
+        config_client
+            .set_log_level(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Self::get_log_levels(client).await
+    }
+    
+    /// List the settings the server knows, optionally for one category
+    pub async fn get_settings_schema(
+        client: &GrpcClient,
+        category: Option<&str>,
+    ) -> Result<Vec<SettingSchemaInfo>, CommandError> {
+        let request = GetSettingsSchemaRequest {
+            category: category.unwrap_or("").to_string(),
+        };
+
+        let mut client = client.config.clone();
+        let response = client
+            .get_settings_schema(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(response.settings.into_iter().map(|s| {
+            let meta = s.metadata.unwrap_or_default();
+            let value_type = match ConfigType::try_from(meta.r#type).unwrap_or(ConfigType::Unknown) {
+                ConfigType::Integer => "integer",
+                ConfigType::Float => "float",
+                ConfigType::Boolean => "boolean",
+                ConfigType::Json => "json",
+                _ => "string",
+            };
+            SettingSchemaInfo {
+                key: s.key,
+                category: meta.category,
+                description: meta.description,
+                value_type: value_type.to_string(),
+                default_value: if meta.default_value.is_empty() { None } else { Some(meta.default_value) },
+                allowed_values: meta.allowed_values,
+                min: s.min,
+                max: s.max,
+                requires_restart: s.requires_restart,
+                current_value: s.current_value,
+                is_set: s.is_set,
+            }
+        }).collect())
+    }
+
+    /// Show the updater's last known state without contacting the feed
+    pub async fn get_update_status(
+        client: &GrpcClient,
+    ) -> Result<UpdateStatusInfo, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .get_update_status(GetUpdateStatusRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(Self::update_status_from_proto(response))
+    }
+
+    /// Read the release feed now
+    pub async fn check_for_update(
+        client: &GrpcClient,
+    ) -> Result<UpdateStatusInfo, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .check_for_update(CheckForUpdateRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(Self::update_status_from_proto(response))
+    }
+
+    /// Download, verify and install the latest release, then restart the server
+    pub async fn apply_update(
+        client: &GrpcClient,
+        restart_delay_seconds: Option<i32>,
+    ) -> Result<ApplyUpdateResult, CommandError> {
+        let request = ApplyUpdateRequest {
+            restart_delay_seconds: restart_delay_seconds.unwrap_or(0),
+        };
+
+        let mut client = client.config.clone();
+        let response = client
+            .apply_update(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(ApplyUpdateResult {
+            installed: response.installed,
+            version: response.version,
+            message: response.message,
+            restart_at: response.restart_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)),
+        })
+    }
+
+    /// Database size, snapshots and the last integrity report
+    pub async fn get_database_status(
+        client: &GrpcClient,
+    ) -> Result<DatabaseStatusResult, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .get_database_status(GetDatabaseStatusRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(DatabaseStatusResult {
+            server_version: response.server_version,
+            embedded: response.embedded,
+            database_bytes: response.database_bytes,
+            wal_bytes: response.wal_bytes,
+            snapshots: response.snapshots.into_iter().map(Self::snapshot_from_proto).collect(),
+            integrity: response.integrity.map(Self::integrity_from_proto),
+            last_compacted_at: response.last_compacted_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)),
+        })
+    }
+
+    /// Take a pg_dump snapshot now
+    pub async fn create_database_snapshot(
+        client: &GrpcClient,
+        label: Option<&str>,
+    ) -> Result<SnapshotInfo, CommandError> {
+        let request = CreateDatabaseSnapshotRequest {
+            label: label.unwrap_or("").to_string(),
+        };
+        let mut client = client.config.clone();
+        let response = client
+            .create_database_snapshot(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(Self::snapshot_from_proto(response))
+    }
+
+    /// Checkpoint, VACUUM (FULL when `full`) and rotate the Postgres log
+    pub async fn compact_database(
+        client: &GrpcClient,
+        full: bool,
+    ) -> Result<CompactResult, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .compact_database(CompactDatabaseRequest { full })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        Ok(CompactResult {
+            database_bytes_before: response.database_bytes_before,
+            database_bytes_after: response.database_bytes_after,
+            wal_bytes_before: response.wal_bytes_before,
+            wal_bytes_after: response.wal_bytes_after,
+            log_rotated: response.log_rotated,
+            duration_ms: response.duration_ms,
+            warnings: response.warnings,
+        })
+    }
+
+    /// Run the database integrity checks now
+    pub async fn check_database_integrity(
+        client: &GrpcClient,
+    ) -> Result<IntegrityInfo, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .check_database_integrity(CheckDatabaseIntegrityRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(Self::integrity_from_proto(response))
+    }
+
+    /// Apply the retention limits now, or with `dry_run` only count what would be deleted
+    pub async fn run_retention(
+        client: &GrpcClient,
+        dry_run: bool,
+    ) -> Result<RetentionReport, CommandError> {
+        let mut client = client.config.clone();
+        Ok(client
+            .run_retention(RunRetentionRequest { dry_run })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner())
+    }
+
+    /// SANs, fingerprint and mutual TLS state of the server certificate
+    pub async fn get_certificate_info(
+        client: &GrpcClient,
+    ) -> Result<CertificateInfo, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .get_certificate_info(GetCertificateInfoRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(Self::certificate_from_proto(response))
+    }
+
+    /// Re-issue the server certificate; non-empty `extra_sans` replace tls.extra_sans first
+    pub async fn regenerate_certificate(
+        client: &GrpcClient,
+        extra_sans: Vec<String>,
+    ) -> Result<CertificateInfo, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .regenerate_certificate(RegenerateCertificateRequest { extra_sans })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(Self::certificate_from_proto(response))
+    }
+
+    /// Issue a client certificate for mutual TLS
+    pub async fn issue_client_certificate(
+        client: &GrpcClient,
+        name: &str,
+    ) -> Result<ClientCertificate, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .issue_client_certificate(IssueClientCertificateRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(ClientCertificate {
+            cert_pem: response.cert_pem,
+            key_pem: response.key_pem,
+            ca_pem: response.ca_pem,
+            fingerprint: response.fingerprint,
+        })
+    }
+
+    fn certificate_from_proto(c: CertificateInfoProto) -> CertificateInfo {
+        CertificateInfo {
+            sans: c.sans,
+            generated_at: c.generated_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)),
+            fingerprint: c.fingerprint,
+            has_ca: c.has_ca,
+            require_client_cert: c.require_client_cert,
+            restart_required: c.restart_required,
+        }
+    }
+
+    fn snapshot_from_proto(s: DatabaseSnapshot) -> SnapshotInfo {
+        SnapshotInfo {
+            name: s.name,
+            size_bytes: s.size_bytes,
+            created_at: s.created_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)),
+        }
+    }
+
+    fn integrity_from_proto(r: DatabaseIntegrityReport) -> IntegrityInfo {
+        IntegrityInfo {
+            ok: r.ok,
+            errors: r.errors,
+            warnings: r.warnings,
+            checked_at: r.checked_at
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)),
+        }
+    }
+
+    fn update_status_from_proto(status: UpdateStatus) -> UpdateStatusInfo {
+        let ts = |ts: maowbot_proto::prost_types::Timestamp| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32);
+        UpdateStatusInfo {
+            phase: status.phase,
+            current_version: status.current_version,
+            latest_version: if status.latest_version.is_empty() { None } else { Some(status.latest_version) },
+            release_notes: status.release_notes,
+            published_at: status.published_at.and_then(ts),
+            last_checked: status.last_checked.and_then(ts),
+            last_error: if status.last_error.is_empty() { None } else { Some(status.last_error) },
+            update_available: status.update_available,
+            auto_update: status.auto_update,
+            feed_url: status.feed_url,
+            has_public_key: status.has_public_key,
+            target: status.target,
+        }
+    }
+
+    /// Re-encrypt all stored secrets under a new master key
+    pub async fn rotate_encryption_key(
+        client: &GrpcClient,
+        dry_run: bool,
+    ) -> Result<RotateKeyResult, CommandError> {
+        let request = RotateEncryptionKeyRequest { dry_run };
+        
+        let mut client = client.config.clone();
+        let response = client
+            .rotate_encryption_key(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+            
+        let response = response.into_inner();
+        
+        Ok(RotateKeyResult {
+            message: response.message,
+            key_source: response.key_source,
+            columns: response.columns.into_iter().map(|c| (c.column, c.rows)).collect(),
+            total: response.total,
+        })
+    }
+
+    /// Export the whole database as a single archive
+    pub async fn export_backup(
+        client: &GrpcClient,
+    ) -> Result<ExportBackupResult, CommandError> {
+        let mut client = client.config.clone();
+        let response = client
+            .export_backup(ExportBackupRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let response = response.into_inner();
+
+        Ok(ExportBackupResult {
+            archive: response.archive,
+            backup_key: response.backup_key,
+            schema_version: response.schema_version,
+            counts: response.counts.unwrap_or_default(),
+        })
+    }
+
+    /// Restore an archive from `export_backup`. Without a backup key,
+    /// credentials and platform secrets are left out.
+    pub async fn import_backup(
+        client: &GrpcClient,
+        archive: Vec<u8>,
+        backup_key: Option<&str>,
+    ) -> Result<ImportBackupResult, CommandError> {
+        let request = ImportBackupRequest {
+            archive,
+            backup_key: backup_key.unwrap_or_default().to_string(),
+        };
+
+        let mut client = client.config.clone();
+        let response = client
+            .import_backup(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let response = response.into_inner();
+
+        Ok(ImportBackupResult {
+            restored: response.restored.unwrap_or_default(),
+            skipped: response.skipped.unwrap_or_default(),
+            warnings: response.warnings,
+            schema_version: response.schema_version,
+        })
+    }
+}
@@ -163,6 +163,7 @@ impl ConnectivityCommands {
             "discord" => maowbot_proto::maowbot::common::Platform::Discord,
             "vrchat" => maowbot_proto::maowbot::common::Platform::Vrchat,
             "vrchat-pipeline" | "vrchatpipeline" => maowbot_proto::maowbot::common::Platform::VrchatPipeline,
+            "kick" => maowbot_proto::maowbot::common::Platform::Kick,
             _ => return Err(CommandError::InvalidInput(format!("Unknown platform: {}", platform))),
         };
             
@@ -205,7 +206,7 @@ impl ConnectivityCommands {
                 Ok(resp) => {
                     resp.into_inner()
                         .user
-                        .and_then(|u| Some(u.global_username))
+                        .map(|u| u.global_username)
                         .unwrap_or_else(|| credential.user_id.clone())
                 }
                 Err(_) => credential.user_id.clone(),
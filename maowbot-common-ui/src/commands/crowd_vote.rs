@@ -0,0 +1,79 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    CrowdVote, CrowdVoteOption, CrowdVoteResponse, StartVoteRequest, VoteChannelRequest,
+};
+
+/// Crowd control vote command handlers
+pub struct CrowdVoteCommands;
+
+fn vote_of(response: CrowdVoteResponse) -> Result<CrowdVote, CommandError> {
+    response.vote.ok_or_else(|| CommandError::DataError("Server returned no vote".to_string()))
+}
+
+fn channel_request(channel: Option<&str>) -> VoteChannelRequest {
+    VoteChannelRequest { channel: channel.unwrap_or_default().to_string() }
+}
+
+impl CrowdVoteCommands {
+    /// Opens a vote among `(label, control)` options; an empty channel means
+    /// the broadcaster's
+    pub async fn start(
+        client: &GrpcClient,
+        channel: Option<&str>,
+        title: &str,
+        duration_seconds: u32,
+        options: Vec<(String, Option<String>)>,
+    ) -> Result<CrowdVote, CommandError> {
+        let request = StartVoteRequest {
+            title: title.to_string(),
+            channel: channel.unwrap_or_default().to_string(),
+            duration_seconds,
+            options: options.into_iter().map(|(label, control)| CrowdVoteOption {
+                label,
+                control: control.unwrap_or_default(),
+                votes: 0,
+                weight: 0,
+            }).collect(),
+        };
+        let mut vote_client = client.crowd_vote.clone();
+        let response = vote_client
+            .start_vote(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        vote_of(response)
+    }
+
+    /// The open vote, or the last one
+    pub async fn get(client: &GrpcClient, channel: Option<&str>) -> Result<CrowdVote, CommandError> {
+        let mut vote_client = client.crowd_vote.clone();
+        let response = vote_client
+            .get_vote(channel_request(channel))
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        vote_of(response)
+    }
+
+    /// Decide the open vote now
+    pub async fn end(client: &GrpcClient, channel: Option<&str>) -> Result<CrowdVote, CommandError> {
+        let mut vote_client = client.crowd_vote.clone();
+        let response = vote_client
+            .end_vote(channel_request(channel))
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        vote_of(response)
+    }
+
+    pub async fn cancel(client: &GrpcClient, channel: Option<&str>) -> Result<CrowdVote, CommandError> {
+        let mut vote_client = client.crowd_vote.clone();
+        let response = vote_client
+            .cancel_vote(channel_request(channel))
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        vote_of(response)
+    }
+}
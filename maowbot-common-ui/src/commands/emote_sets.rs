@@ -0,0 +1,42 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{ListChannelEmotesRequest, ListChannelEmotesResponse};
+
+/// Third-party emote set handlers
+pub struct EmoteSetCommands;
+
+impl EmoteSetCommands {
+    /// 7TV, BTTV and FFZ emotes of `channel` (empty for the broadcaster's),
+    /// as the bot last fetched them.
+    pub async fn list_channel_emotes(
+        client: &GrpcClient,
+        channel: &str,
+        include_globals: bool,
+    ) -> Result<ListChannelEmotesResponse, CommandError> {
+        let mut sets_client = client.emote_sets.clone();
+        let response = sets_client
+            .list_channel_emotes(ListChannelEmotesRequest {
+                channel: channel.to_string(),
+                include_globals,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Has the bot refetch `channel`'s emotes now.
+    pub async fn refresh_channel_emotes(
+        client: &GrpcClient,
+        channel: &str,
+    ) -> Result<ListChannelEmotesResponse, CommandError> {
+        let mut sets_client = client.emote_sets.clone();
+        let response = sets_client
+            .refresh_channel_emotes(ListChannelEmotesRequest {
+                channel: channel.to_string(),
+                include_globals: false,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{GetEmoteStatsRequest, GetEmoteStatsResponse};
+
+/// Emote usage query handlers
+pub struct EmoteStatsCommands;
+
+impl EmoteStatsCommands {
+    /// Top emotes of `channel` (empty for the broadcaster's). Set `days` for
+    /// whole days, otherwise `window_minutes` (1-60) is used.
+    pub async fn top_emotes(
+        client: &GrpcClient,
+        channel: &str,
+        window_minutes: i32,
+        days: i32,
+        limit: i32,
+    ) -> Result<GetEmoteStatsResponse, CommandError> {
+        let mut stats_client = client.emote_stats.clone();
+        let response = stats_client
+            .get_emote_stats(GetEmoteStatsRequest {
+                channel: channel.to_string(),
+                window_minutes,
+                days,
+                limit,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+}
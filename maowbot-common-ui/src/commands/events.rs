@@ -0,0 +1,73 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{SubscribeEventsRequest, UiEvent, UiEventKind};
+use tonic::Streaming;
+
+/// Follows the server's `SubscribeEvents` stream across reconnects: each
+/// reconnect resumes after the last event received, so nothing is missed
+/// while the journal still has it (a `Gap` event says when it didn't).
+pub struct EventFeed {
+    kinds: Vec<UiEventKind>,
+    resume_token: String,
+    stream: Option<Streaming<UiEvent>>,
+}
+
+impl EventFeed {
+    /// A feed of `kinds` (all kinds when empty), starting with live events.
+    pub fn new(kinds: Vec<UiEventKind>) -> Self {
+        Self { kinds, resume_token: String::new(), stream: None }
+    }
+
+    /// A feed picking up after `resume_token` from an earlier feed.
+    pub fn resume(kinds: Vec<UiEventKind>, resume_token: String) -> Self {
+        Self { kinds, resume_token, stream: None }
+    }
+
+    /// The token of the last event received; empty before the first.
+    pub fn resume_token(&self) -> &str {
+        &self.resume_token
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// The next event. A dropped stream is reopened once; if that fails the
+    /// error is returned and the next call tries again.
+    pub async fn next(&mut self, client: &GrpcClient) -> Result<UiEvent, CommandError> {
+        let mut reconnected = false;
+        loop {
+            let stream = match self.stream.as_mut() {
+                Some(stream) => stream,
+                None => {
+                    let request = SubscribeEventsRequest {
+                        kinds: self.kinds.iter().map(|k| *k as i32).collect(),
+                        resume_token: self.resume_token.clone(),
+                    };
+                    let stream = client.events.clone()
+                        .subscribe_events(request)
+                        .await
+                        .map_err(|e| CommandError::GrpcError(e.to_string()))?
+                        .into_inner();
+                    reconnected = true;
+                    self.stream.insert(stream)
+                }
+            };
+            match stream.message().await {
+                Ok(Some(event)) => {
+                    self.resume_token = event.resume_token.clone();
+                    return Ok(event);
+                }
+                Ok(None) | Err(_) if !reconnected => self.stream = None,
+                Ok(None) => {
+                    self.stream = None;
+                    return Err(CommandError::GrpcError("Event stream closed by the server".to_string()));
+                }
+                Err(e) => {
+                    self.stream = None;
+                    return Err(CommandError::GrpcError(e.to_string()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::GrpcClient;
+use super::{CommandError, to_timestamp};
+use maowbot_proto::maowbot::services::{ExportChunk, ExportRequest};
+
+/// What `export` sends; empty strings and `None` use the server's defaults
+#[derive(Default)]
+pub struct ExportFilter {
+    /// "chat", "events" or "commands"
+    pub kind: String,
+    /// "csv" or "jsonl"
+    pub format: String,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    pub platform: String,
+    pub channel: String,
+}
+
+/// Export command handlers
+pub struct ExportCommands;
+
+impl ExportCommands {
+    /// Starts an export; read the chunks with `message()` and write their
+    /// data to the file in order
+    pub async fn export(
+        client: &GrpcClient,
+        filter: ExportFilter,
+    ) -> Result<tonic::Streaming<ExportChunk>, CommandError> {
+        let request = ExportRequest {
+            kind: filter.kind,
+            format: filter.format,
+            since: filter.since.map(to_timestamp),
+            until: filter.until.map(to_timestamp),
+            platform: filter.platform,
+            channel: filter.channel,
+        };
+        client.export.clone()
+            .export(request)
+            .await
+            .map(|r| r.into_inner())
+            .map_err(|e| CommandError::GrpcError(e.to_string()))
+    }
+}
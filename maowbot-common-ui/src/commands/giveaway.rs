@@ -0,0 +1,167 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    Giveaway, GiveawayEntry, GiveawayRules,
+    OpenGiveawayRequest, GiveawayIdRequest, DrawWinnersRequest, RerollWinnerRequest,
+    ListGiveawaysRequest,
+};
+
+/// How to open a giveaway; `keyword` or `points_cost` (or both) must be set
+pub struct NewGiveaway {
+    pub title: String,
+    /// Empty for the broadcaster's channel
+    pub channel: String,
+    pub keyword: String,
+    pub points_cost: i64,
+    pub sub_only: bool,
+    pub min_follow_days: i32,
+    pub exclude_winners_days: i32,
+    pub sub_weight: i32,
+}
+
+impl Default for NewGiveaway {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            channel: String::new(),
+            keyword: String::new(),
+            points_cost: 0,
+            sub_only: false,
+            min_follow_days: 0,
+            exclude_winners_days: 0,
+            sub_weight: 1,
+        }
+    }
+}
+
+/// A giveaway with its entries
+pub struct GiveawayDetails {
+    pub giveaway: Giveaway,
+    pub entries: Vec<GiveawayEntry>,
+}
+
+/// Giveaway command handlers
+pub struct GiveawayCommands;
+
+impl GiveawayCommands {
+    pub async fn open(
+        client: &GrpcClient,
+        new: NewGiveaway,
+    ) -> Result<Giveaway, CommandError> {
+        let request = OpenGiveawayRequest {
+            title: new.title,
+            channel: new.channel,
+            keyword: new.keyword,
+            points_cost: new.points_cost,
+            rules: Some(GiveawayRules {
+                sub_only: new.sub_only,
+                min_follow_days: new.min_follow_days,
+                exclude_winners_days: new.exclude_winners_days,
+                sub_weight: new.sub_weight,
+            }),
+        };
+
+        let mut giveaway_client = client.giveaway.clone();
+        giveaway_client
+            .open_giveaway(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .giveaway
+            .ok_or_else(|| CommandError::DataError("Server returned no giveaway".to_string()))
+    }
+
+    /// Stop taking entries
+    pub async fn close(
+        client: &GrpcClient,
+        giveaway_id: &str,
+    ) -> Result<Giveaway, CommandError> {
+        let mut giveaway_client = client.giveaway.clone();
+        giveaway_client
+            .close_giveaway(GiveawayIdRequest { giveaway_id: giveaway_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .giveaway
+            .ok_or_else(|| CommandError::DataError("Server returned no giveaway".to_string()))
+    }
+
+    pub async fn cancel(
+        client: &GrpcClient,
+        giveaway_id: &str,
+    ) -> Result<Giveaway, CommandError> {
+        let mut giveaway_client = client.giveaway.clone();
+        giveaway_client
+            .cancel_giveaway(GiveawayIdRequest { giveaway_id: giveaway_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .giveaway
+            .ok_or_else(|| CommandError::DataError("Server returned no giveaway".to_string()))
+    }
+
+    /// Draw `count` winners, closing the giveaway first if needed
+    pub async fn draw(
+        client: &GrpcClient,
+        giveaway_id: &str,
+        count: i32,
+    ) -> Result<Vec<GiveawayEntry>, CommandError> {
+        let mut giveaway_client = client.giveaway.clone();
+        let response = giveaway_client
+            .draw_winners(DrawWinnersRequest { giveaway_id: giveaway_id.to_string(), count })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(response.winners)
+    }
+
+    /// Replace `username` (or the latest winner) with a new draw
+    pub async fn reroll(
+        client: &GrpcClient,
+        giveaway_id: &str,
+        username: Option<&str>,
+    ) -> Result<GiveawayEntry, CommandError> {
+        let mut giveaway_client = client.giveaway.clone();
+        giveaway_client
+            .reroll_winner(RerollWinnerRequest {
+                giveaway_id: giveaway_id.to_string(),
+                username: username.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .winner
+            .ok_or_else(|| CommandError::DataError("Server returned no winner".to_string()))
+    }
+
+    /// Newest first
+    pub async fn list(
+        client: &GrpcClient,
+        limit: i32,
+    ) -> Result<Vec<Giveaway>, CommandError> {
+        let mut giveaway_client = client.giveaway.clone();
+        let response = giveaway_client
+            .list_giveaways(ListGiveawaysRequest { limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(response.giveaways)
+    }
+
+    pub async fn get(
+        client: &GrpcClient,
+        giveaway_id: &str,
+    ) -> Result<GiveawayDetails, CommandError> {
+        let mut giveaway_client = client.giveaway.clone();
+        let response = giveaway_client
+            .get_giveaway(GiveawayIdRequest { giveaway_id: giveaway_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        Ok(GiveawayDetails {
+            giveaway: response.giveaway
+                .ok_or_else(|| CommandError::DataError("Server returned no giveaway".to_string()))?,
+            entries: response.entries,
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{GetReadinessRequest, GetReadinessResponse, ReadinessState};
+use tonic::Code;
+
+/// How often `wait_ready` asks again.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Startup readiness command handlers
+pub struct HealthCommands;
+
+impl HealthCommands {
+    pub async fn get_readiness(client: &GrpcClient) -> Result<GetReadinessResponse, CommandError> {
+        let mut health_client = client.health.clone();
+        let response = health_client
+            .get_readiness(GetReadinessRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Polls until every subsystem is up or `timeout` passes. `on_wait` gets a
+    /// line describing what the server is still busy with, once per change.
+    /// Servers without the health service count as ready.
+    pub async fn wait_ready(
+        client: &GrpcClient,
+        timeout: Duration,
+        mut on_wait: impl FnMut(&str),
+    ) -> Result<GetReadinessResponse, CommandError> {
+        let deadline = Instant::now() + timeout;
+        let mut last_reported = String::new();
+        loop {
+            let mut health_client = client.health.clone();
+            let waiting = match health_client.get_readiness(GetReadinessRequest {}).await {
+                Ok(response) => {
+                    let readiness = response.into_inner();
+                    if readiness.ready {
+                        return Ok(readiness);
+                    }
+                    if let Some(failed) = readiness.subsystems.iter()
+                        .find(|s| s.state == ReadinessState::Failed as i32)
+                    {
+                        return Err(CommandError::GrpcError(format!(
+                            "The server failed to start {}: {}", failed.name, failed.message
+                        )));
+                    }
+                    describe_waiting(&readiness)
+                }
+                Err(status) if status.code() == Code::Unimplemented => {
+                    return Ok(GetReadinessResponse { ready: true, ..Default::default() });
+                }
+                Err(status) => format!("waiting for the server ({})", status.message()),
+            };
+
+            if waiting != last_reported {
+                on_wait(&waiting);
+                last_reported = waiting;
+            }
+            if Instant::now() + POLL_INTERVAL > deadline {
+                return Err(CommandError::GrpcError(format!(
+                    "The server did not finish starting within {}s ({})", timeout.as_secs(), last_reported
+                )));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// "waiting on platforms (starting)" for the first subsystem that isn't up.
+pub fn describe_waiting(readiness: &GetReadinessResponse) -> String {
+    readiness.subsystems.iter()
+        .find(|s| !is_up(s.state))
+        .map(|s| format!("waiting on {} ({})", s.name, state_name(s.state)))
+        .unwrap_or_else(|| "finishing startup".to_string())
+}
+
+pub fn is_up(state: i32) -> bool {
+    state == ReadinessState::Ready as i32 || state == ReadinessState::Degraded as i32
+}
+
+pub fn state_name(state: i32) -> &'static str {
+    match ReadinessState::try_from(state) {
+        Ok(ReadinessState::Pending) => "pending",
+        Ok(ReadinessState::Starting) => "starting",
+        Ok(ReadinessState::Ready) => "ready",
+        Ok(ReadinessState::Degraded) => "degraded",
+        Ok(ReadinessState::Failed) => "failed",
+        Err(_) => "unknown",
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    DeleteLocalizedMessageRequest, ListChannelLanguagesRequest, ListChannelLanguagesResponse, ListLanguagesRequest,
+    ListLanguagesResponse, ListMessagesRequest, ListMessagesResponse, SetChannelLanguageRequest, SetMessagesRequest,
+};
+
+/// Language pack and channel language handlers
+pub struct LocalizationCommands;
+
+impl LocalizationCommands {
+    pub async fn list_languages(client: &GrpcClient) -> Result<ListLanguagesResponse, CommandError> {
+        let mut localization = client.localization.clone();
+        let response = localization
+            .list_languages(ListLanguagesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Every message key in `language` (empty for the default language).
+    pub async fn list_messages(client: &GrpcClient, language: &str) -> Result<ListMessagesResponse, CommandError> {
+        let mut localization = client.localization.clone();
+        let response = localization
+            .list_messages(ListMessagesRequest { language: language.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Stores templates by key; returns how many were stored.
+    pub async fn set_messages(
+        client: &GrpcClient,
+        language: &str,
+        messages: HashMap<String, String>,
+    ) -> Result<i32, CommandError> {
+        let mut localization = client.localization.clone();
+        let response = localization
+            .set_messages(SetMessagesRequest { language: language.to_string(), messages })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().stored)
+    }
+
+    pub async fn delete_message(client: &GrpcClient, language: &str, key: &str) -> Result<bool, CommandError> {
+        let mut localization = client.localization.clone();
+        let response = localization
+            .delete_message(DeleteLocalizedMessageRequest { language: language.to_string(), key: key.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().removed)
+    }
+
+    pub async fn list_channel_languages(client: &GrpcClient) -> Result<ListChannelLanguagesResponse, CommandError> {
+        let mut localization = client.localization.clone();
+        let response = localization
+            .list_channel_languages(ListChannelLanguagesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Sets a channel's language; an empty `language` returns it to the default.
+    pub async fn set_channel_language(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+        language: &str,
+    ) -> Result<(), CommandError> {
+        let mut localization = client.localization.clone();
+        localization
+            .set_channel_language(SetChannelLanguageRequest {
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+                language: language.to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 use crate::GrpcClient;
-use super::CommandError;
+use super::{CommandError, MAX_PAGE_SIZE};
 use maowbot_proto::maowbot::services::{
     GetUserRequest, SearchUsersRequest, SearchField, MergeUsersRequest,
     GetPlatformIdentitiesRequest, AddRoleToIdentityRequest, RemoveRoleFromIdentityRequest,
     GetUserAnalysisRequest, AppendModeratorNoteRequest, FindUserByNameRequest,
-    MergeStrategy,
+    MergeStrategy, AddUserNoteRequest, ListUserNotesRequest, DeleteUserNoteRequest,
+    RecordModerationActionRequest, ListModerationHistoryRequest, UserNote, ModerationAction,
+    GetViewerCardRequest, GetViewerCardResponse,
+    StartAnalysisRecomputeRequest, GetAnalysisRecomputeStatusRequest,
+    CancelAnalysisRecomputeRequest, AnalysisRecomputeRun,
 };
-use maowbot_proto::maowbot::common::{User, PlatformIdentity, UserAnalysis};
+use maowbot_proto::maowbot::common::{User, PlatformIdentity, UserAnalysis, PageRequest};
 use uuid::Uuid;
 
 /// Result of user info query
@@ -14,8 +18,24 @@ pub struct UserInfoResult {
     pub user: User,
     pub identities: Vec<PlatformIdentity>,
     pub analysis: Option<UserAnalysis>,
+    /// Most recent notes, newest first (empty if the caller may not read them)
+    pub notes: Vec<UserNote>,
+    /// Most recent timeouts/bans/warnings across platforms, newest first
+    pub moderation_history: Vec<ModerationAction>,
 }
 
+/// A moderation action to record by hand (e.g. a warning given in voice chat)
+pub struct NewModerationAction<'a> {
+    pub action_type: &'a str,
+    pub platform: &'a str,
+    pub channel: &'a str,
+    pub reason: &'a str,
+    pub duration_seconds: i32,
+}
+
+/// How many notes/actions `get_user_info` includes.
+const INFO_HISTORY_LIMIT: i32 = 5;
+
 /// Result of user search
 pub struct UserSearchResult {
     pub users: Vec<User>,
@@ -61,38 +81,60 @@ impl MemberCommands {
             Ok(response) => response.into_inner().analysis,
             Err(_) => None,
         };
+
+        // Read-only tokens may not see notes or history; show the rest anyway
+        let notes = user_client
+            .list_user_notes(ListUserNotesRequest { user_id: user.user_id.clone(), limit: INFO_HISTORY_LIMIT })
+            .await
+            .map(|r| r.into_inner().notes)
+            .unwrap_or_default();
+        let moderation_history = user_client
+            .list_moderation_history(ListModerationHistoryRequest { user_id: user.user_id.clone(), limit: INFO_HISTORY_LIMIT })
+            .await
+            .map(|r| r.into_inner().actions)
+            .unwrap_or_default();
         
         Ok(UserInfoResult {
             user,
             identities,
             analysis,
+            notes,
+            moderation_history,
         })
     }
     
-    /// Search for users
+    /// Search for users, following page tokens to the end
     pub async fn search_users(
         client: &GrpcClient,
         query: &str,
     ) -> Result<UserSearchResult, CommandError> {
-        let request = SearchUsersRequest {
-            query: query.to_string(),
-            search_fields: vec![SearchField::All as i32],
-            page: None,
-        };
-        
-        let mut user_client = client.user.clone();
-        let response = user_client
-            .search_users(request)
-            .await
-            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
-            
-        let users = response
-            .into_inner()
-            .results
-            .into_iter()
-            .filter_map(|r| r.user)
-            .collect();
-            
+        let mut users = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let request = SearchUsersRequest {
+                query: query.to_string(),
+                search_fields: vec![SearchField::All as i32],
+                page: Some(PageRequest {
+                    page_size: MAX_PAGE_SIZE,
+                    page_token,
+                }),
+                read_mask: None,
+            };
+
+            let mut user_client = client.user.clone();
+            let response = user_client
+                .search_users(request)
+                .await
+                .map_err(|e| CommandError::GrpcError(e.to_string()))?
+                .into_inner();
+
+            users.extend(response.results.into_iter().filter_map(|r| r.user));
+            page_token = response.page.map(|p| p.next_page_token).unwrap_or_default();
+            if page_token.is_empty() {
+                break;
+            }
+        }
+
         Ok(UserSearchResult { users })
     }
     
@@ -119,6 +161,167 @@ impl MemberCommands {
         Ok(())
     }
     
+    /// Attach a timestamped note to a user
+    pub async fn add_user_note(
+        client: &GrpcClient,
+        identifier: &str,
+        note_text: &str,
+    ) -> Result<UserNote, CommandError> {
+        let user = Self::resolve_user(client, identifier).await?;
+
+        let request = AddUserNoteRequest {
+            user_id: user.user_id,
+            note_text: note_text.to_string(),
+            author: String::new(), // Server credits the calling token
+        };
+
+        let mut user_client = client.user.clone();
+        user_client
+            .add_user_note(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .note
+            .ok_or_else(|| CommandError::GrpcError("Server returned no note".to_string()))
+    }
+
+    /// List a user's notes, newest first
+    pub async fn list_user_notes(
+        client: &GrpcClient,
+        identifier: &str,
+        limit: i32,
+    ) -> Result<Vec<UserNote>, CommandError> {
+        let user = Self::resolve_user(client, identifier).await?;
+
+        let mut user_client = client.user.clone();
+        Ok(user_client
+            .list_user_notes(ListUserNotesRequest { user_id: user.user_id, limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .notes)
+    }
+
+    /// Delete a note by its ID
+    pub async fn delete_user_note(
+        client: &GrpcClient,
+        note_id: &str,
+    ) -> Result<(), CommandError> {
+        if Uuid::parse_str(note_id).is_err() {
+            return Err(CommandError::InvalidInput(format!("Invalid note ID: {}", note_id)));
+        }
+
+        let mut user_client = client.user.clone();
+        user_client
+            .delete_user_note(DeleteUserNoteRequest { note_id: note_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Record a timeout, ban, unban or warning that the bot did not see itself
+    pub async fn record_moderation_action(
+        client: &GrpcClient,
+        identifier: &str,
+        action: NewModerationAction<'_>,
+    ) -> Result<ModerationAction, CommandError> {
+        let user = Self::resolve_user(client, identifier).await?;
+
+        let request = RecordModerationActionRequest {
+            user_id: user.user_id,
+            platform: action.platform.to_string(),
+            channel: action.channel.to_string(),
+            action_type: action.action_type.to_string(),
+            reason: action.reason.to_string(),
+            duration_seconds: action.duration_seconds,
+            moderator: String::new(), // Server credits the calling token
+        };
+
+        let mut user_client = client.user.clone();
+        user_client
+            .record_moderation_action(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .action
+            .ok_or_else(|| CommandError::GrpcError("Server returned no action".to_string()))
+    }
+
+    /// A user's moderation history across platforms, newest first
+    pub async fn moderation_history(
+        client: &GrpcClient,
+        identifier: &str,
+        limit: i32,
+    ) -> Result<Vec<ModerationAction>, CommandError> {
+        let user = Self::resolve_user(client, identifier).await?;
+
+        let mut user_client = client.user.clone();
+        Ok(user_client
+            .list_moderation_history(ListModerationHistoryRequest { user_id: user.user_id, limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .actions)
+    }
+
+    /// Everything known about a viewer across platforms, with up to
+    /// `message_limit` recent chat messages (0 for the server's default)
+    pub async fn viewer_card(
+        client: &GrpcClient,
+        identifier: &str,
+        message_limit: i32,
+    ) -> Result<GetViewerCardResponse, CommandError> {
+        let user = Self::resolve_user(client, identifier).await?;
+
+        let mut user_client = client.user.clone();
+        Ok(user_client
+            .get_viewer_card(GetViewerCardRequest { user: user.user_id, message_limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner())
+    }
+
+    /// Starts recomputing every user's analysis scores over the last `days`
+    /// days of chat (all of it with `None`), or resumes the latest unfinished run
+    pub async fn start_analysis_recompute(
+        client: &GrpcClient,
+        days: Option<i32>,
+        resume: bool,
+    ) -> Result<AnalysisRecomputeRun, CommandError> {
+        let mut user_client = client.user.clone();
+        Ok(user_client
+            .start_analysis_recompute(StartAnalysisRecomputeRequest { days: days.unwrap_or(0), resume })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner())
+    }
+
+    /// The most recent analysis recompute run, if any
+    pub async fn analysis_recompute_status(
+        client: &GrpcClient,
+    ) -> Result<Option<AnalysisRecomputeRun>, CommandError> {
+        let mut user_client = client.user.clone();
+        Ok(user_client
+            .get_analysis_recompute_status(GetAnalysisRecomputeStatusRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .run)
+    }
+
+    /// Stops the running analysis recompute after its current batch
+    pub async fn cancel_analysis_recompute(
+        client: &GrpcClient,
+    ) -> Result<AnalysisRecomputeRun, CommandError> {
+        let mut user_client = client.user.clone();
+        Ok(user_client
+            .cancel_analysis_recompute(CancelAnalysisRecomputeRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner())
+    }
+
     /// Merge users
     pub async fn merge_users(
         client: &GrpcClient,
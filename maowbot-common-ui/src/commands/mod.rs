@@ -18,6 +18,37 @@ pub mod osc;
 pub mod vrchat;
 pub mod obs;
 pub mod pipeline;
+pub mod workspace;
+pub mod token;
+pub mod audit;
+pub mod chat_archive;
+pub mod giveaway;
+pub mod crowd_vote;
+pub mod protection;
+pub mod moderation_rules;
+pub mod emote_stats;
+pub mod emote_sets;
+pub mod localization;
+pub mod events;
+pub mod responders;
+pub mod alerting;
+pub mod health;
+pub mod stream_sessions;
+pub mod export;
+
+/// The largest page the server's list RPCs hand out; used when walking every page.
+pub const MAX_PAGE_SIZE: i32 = 500;
+
+pub(crate) fn to_timestamp(t: chrono::DateTime<chrono::Utc>) -> maowbot_proto::prost_types::Timestamp {
+    maowbot_proto::prost_types::Timestamp {
+        seconds: t.timestamp(),
+        nanos: t.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub(crate) fn to_datetime(ts: Option<maowbot_proto::prost_types::Timestamp>) -> Option<chrono::DateTime<chrono::Utc>> {
+    ts.and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32))
+}
 
 /// Result type that can include both data and warnings
 pub struct CommandResult<T> {
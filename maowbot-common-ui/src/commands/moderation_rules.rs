@@ -0,0 +1,108 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    ChatRule, RuleHit, ListRulesRequest, AddRuleRequest, UpdateRuleRequest,
+    DeleteRuleRequest, ResetRuleHitsRequest, TestMessageRequest,
+    CategoryOffense, ListOffensesRequest, PardonUserRequest,
+};
+
+/// Moderation rule command handlers
+pub struct ModerationRuleCommands;
+
+impl ModerationRuleCommands {
+    pub async fn list_rules(client: &GrpcClient) -> Result<Vec<ChatRule>, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        let response = rules_client
+            .list_rules(ListRulesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().rules)
+    }
+
+    pub async fn add_rule(client: &GrpcClient, request: AddRuleRequest) -> Result<ChatRule, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        rules_client
+            .add_rule(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .rule
+            .ok_or_else(|| CommandError::DataError("Server returned no rule".to_string()))
+    }
+
+    /// Change a rule; unset fields stay as they are
+    pub async fn update_rule(client: &GrpcClient, request: UpdateRuleRequest) -> Result<ChatRule, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        rules_client
+            .update_rule(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .rule
+            .ok_or_else(|| CommandError::DataError("Server returned no rule".to_string()))
+    }
+
+    pub async fn delete_rule(client: &GrpcClient, name: &str) -> Result<(), CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        rules_client
+            .delete_rule(DeleteRuleRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn reset_hits(client: &GrpcClient, name: &str) -> Result<ChatRule, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        rules_client
+            .reset_rule_hits(ResetRuleHitsRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .rule
+            .ok_or_else(|| CommandError::DataError("Server returned no rule".to_string()))
+    }
+
+    /// Which rules a message would break, harshest first, without acting on it
+    pub async fn test_message(
+        client: &GrpcClient,
+        text: &str,
+        roles: Vec<String>,
+    ) -> Result<Vec<RuleHit>, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        let response = rules_client
+            .test_message(TestMessageRequest { text: text.to_string(), roles })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().hits)
+    }
+
+    /// Where a user stands on the escalation ladder, per category
+    pub async fn list_offenses(client: &GrpcClient, user: &str) -> Result<Vec<CategoryOffense>, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        let response = rules_client
+            .list_offenses(ListOffensesRequest { user: user.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().offenses)
+    }
+
+    /// Start the ladder over for a user in one category (or all of them when
+    /// empty); returns the pardoned categories
+    pub async fn pardon(
+        client: &GrpcClient,
+        user: &str,
+        category: &str,
+        lift: bool,
+    ) -> Result<Vec<String>, CommandError> {
+        let mut rules_client = client.moderation_rules.clone();
+        let response = rules_client
+            .pardon_user(PardonUserRequest {
+                user: user.to_string(),
+                category: category.to_string(),
+                lift,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().categories)
+    }
+}
@@ -4,8 +4,13 @@ use maowbot_proto::maowbot::services::{
     StartOscRequest, StopOscRequest, RestartOscRequest, GetOscStatusRequest,
     DiscoverPeersRequest, SendChatboxRequest, SendAvatarParameterRequest,
     ListTriggersWithRedeemsRequest, ListActiveTogglesRequest, OscConfig,
+    ExportDripPackRequest, ExportDripPackResponse, PreviewDripPackRequest, PreviewDripPackResponse,
+    ImportDripPackRequest, DripPackImportResult,
+    ListMidiMappingsRequest, ListMidiMappingsResponse, CreateMidiMappingRequest, MidiMapping,
+    SetMidiMappingEnabledRequest, DeleteMidiMappingRequest,
+    ListOscChatControlsRequest, SetOscChatControlRequest, DeleteOscChatControlRequest, OscChatControl,
 };
-use maowbot_proto::maowbot::common::OscTrigger;
+use std::collections::HashMap;
 
 /// OSC service status
 pub struct OscStatus {
@@ -293,6 +298,145 @@ impl OscCommands {
         Ok(toggles)
     }
     
+    /// Export every toggle and its redeem as drip pack JSON
+    pub async fn export_drip_pack(
+        client: &GrpcClient,
+        name: &str,
+        author: &str,
+        description: &str,
+    ) -> Result<ExportDripPackResponse, CommandError> {
+        let request = ExportDripPackRequest {
+            name: name.to_string(),
+            author: author.to_string(),
+            description: description.to_string(),
+        };
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .export_drip_pack(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Check a drip pack and match its parameters to the current avatar
+    pub async fn preview_drip_pack(
+        client: &GrpcClient,
+        pack_json: &str,
+    ) -> Result<PreviewDripPackResponse, CommandError> {
+        let request = PreviewDripPackRequest {
+            pack_json: pack_json.to_string(),
+        };
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .preview_drip_pack(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Import a drip pack with the chosen pack => avatar parameter mapping
+    pub async fn import_drip_pack(
+        client: &GrpcClient,
+        pack_json: &str,
+        parameter_mapping: HashMap<String, String>,
+    ) -> Result<Vec<DripPackImportResult>, CommandError> {
+        let request = ImportDripPackRequest {
+            pack_json: pack_json.to_string(),
+            parameter_mapping,
+        };
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .import_drip_pack(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(response.into_inner().results)
+    }
+
+    /// List MIDI mappings and the open MIDI device
+    pub async fn list_midi_mappings(client: &GrpcClient) -> Result<ListMidiMappingsResponse, CommandError> {
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .list_midi_mappings(ListMidiMappingsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Create a MIDI mapping; with `learn` set the server waits for the next
+    /// note or controller touched and the trigger fields are ignored
+    pub async fn create_midi_mapping(
+        client: &GrpcClient,
+        request: CreateMidiMappingRequest,
+    ) -> Result<MidiMapping, CommandError> {
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .create_midi_mapping(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        response.into_inner().mapping
+            .ok_or_else(|| CommandError::GrpcError("Server returned no mapping".to_string()))
+    }
+
+    /// Turn a MIDI mapping on or off
+    pub async fn set_midi_mapping_enabled(
+        client: &GrpcClient,
+        name: &str,
+        enabled: bool,
+    ) -> Result<MidiMapping, CommandError> {
+        let request = SetMidiMappingEnabledRequest {
+            name: name.to_string(),
+            enabled,
+        };
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .set_midi_mapping_enabled(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        response.into_inner().mapping
+            .ok_or_else(|| CommandError::GrpcError("Server returned no mapping".to_string()))
+    }
+
+    /// Delete a MIDI mapping by name
+    pub async fn delete_midi_mapping(client: &GrpcClient, name: &str) -> Result<(), CommandError> {
+        let mut osc_client = client.osc.clone();
+        osc_client
+            .delete_midi_mapping(DeleteMidiMappingRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(())
+    }
+
+    /// List the avatar parameters chat can drive with !toggle and !set
+    pub async fn list_chat_controls(client: &GrpcClient) -> Result<Vec<OscChatControl>, CommandError> {
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .list_osc_chat_controls(ListOscChatControlsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(response.into_inner().controls)
+    }
+
+    /// Create a chat control, or replace the one with the same name
+    pub async fn set_chat_control(client: &GrpcClient, control: OscChatControl) -> Result<OscChatControl, CommandError> {
+        let mut osc_client = client.osc.clone();
+        let response = osc_client
+            .set_osc_chat_control(SetOscChatControlRequest { control: Some(control) })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        response.into_inner().control
+            .ok_or_else(|| CommandError::GrpcError("Server returned no control".to_string()))
+    }
+
+    /// Delete a chat control by name
+    pub async fn delete_chat_control(client: &GrpcClient, name: &str) -> Result<(), CommandError> {
+        let mut osc_client = client.osc.clone();
+        osc_client
+            .delete_osc_chat_control(DeleteOscChatControlRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.message().to_string()))?;
+        Ok(())
+    }
+
     // Note: Create, Update, Delete trigger operations would need proper proto message updates
     // to match the local OscTrigger model structure
 }
\ No newline at end of file
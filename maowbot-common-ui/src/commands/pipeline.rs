@@ -1,4 +1,6 @@
 use crate::{GrpcClient, CommandResult, CommandError};
+use serde::Deserialize;
+use std::path::Path;
 use maowbot_proto::maowbot::services::event_pipeline::{
     CreatePipelineRequest, UpdatePipelineRequest, DeletePipelineRequest,
     GetPipelineRequest, ListPipelinesRequest, TogglePipelineRequest,
@@ -6,8 +8,10 @@ use maowbot_proto::maowbot::services::event_pipeline::{
     AddActionRequest, UpdateActionRequest, RemoveActionRequest, ListActionsRequest,
     GetAvailableFiltersRequest, GetAvailableActionsRequest,
     GetExecutionHistoryRequest, GetExecutionDetailsRequest,
-    ReloadPipelinesRequest,
-    Pipeline, PipelineFilter, PipelineAction, FilterType, ActionType, ExecutionLog,
+    ReloadPipelinesRequest, TestFirePipelineRequest,
+    SimulateEventRequest, ListSimulatedEventsRequest, SimulatedEventType,
+    GetPipelineThrottleRequest, SetPipelineThrottleRequest, ResetPipelineThrottleRequest,
+    PipelineThrottle, Pipeline, PipelineFilter, PipelineAction, FilterType, ActionType, ExecutionLog,
 };
 
 // Result structures
@@ -88,6 +92,206 @@ pub struct ReloadPipelinesResult {
     pub pipelines_loaded: i32,
 }
 
+pub struct TestFirePipelineResult {
+    pub filters_passed: bool,
+    pub succeeded: bool,
+    pub message: String,
+    pub execution: Option<ExecutionLog>,
+}
+
+pub struct SimulateEventResult {
+    pub event_type: String,
+    pub message: String,
+    pub event_json: String,
+}
+
+pub struct ListSimulatedEventsResult {
+    pub event_types: Vec<SimulatedEventType>,
+}
+
+pub struct PipelineThrottleResult {
+    pub throttle: PipelineThrottle,
+    pub message: String,
+}
+
+pub struct ResetPipelineThrottleResult {
+    pub hits_cleared: i64,
+}
+
+pub struct CreateFromSpecResult {
+    pub pipeline: Pipeline,
+    pub filters_added: usize,
+    pub actions_added: usize,
+}
+
+/// A pipeline definition loaded from a TOML or JSON file.
+///
+/// ```toml
+/// name = "welcome"
+/// description = "Greet first-time chatters"
+/// priority = 50
+///
+/// [[filters]]
+/// type = "platform_filter"
+/// config = { platforms = ["twitch-irc"] }
+///
+/// [[actions]]
+/// type = "twitch_message"
+/// config = { message_template = "Welcome {user}!" }
+///
+/// [throttle]
+/// user_cooldown_seconds = 3600
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub stop_on_match: bool,
+    #[serde(default)]
+    pub stop_on_error: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub filters: Vec<PipelineFilterSpec>,
+    #[serde(default)]
+    pub actions: Vec<PipelineActionSpec>,
+    #[serde(default)]
+    pub throttle: Option<PipelineThrottleSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineFilterSpec {
+    #[serde(rename = "type")]
+    pub filter_type: String,
+    #[serde(default)]
+    pub config: serde_json::Value,
+    #[serde(default)]
+    pub order: Option<i32>,
+    #[serde(default)]
+    pub negated: bool,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineActionSpec {
+    #[serde(rename = "type")]
+    pub action_type: String,
+    #[serde(default)]
+    pub config: serde_json::Value,
+    #[serde(default)]
+    pub order: Option<i32>,
+    #[serde(default)]
+    pub continue_on_error: bool,
+    #[serde(default)]
+    pub is_async: bool,
+    #[serde(default)]
+    pub timeout_ms: Option<i32>,
+    #[serde(default)]
+    pub retry_count: i32,
+    #[serde(default = "default_retry_delay_ms")]
+    pub retry_delay_ms: i32,
+    /// Runs the action only when this holds, e.g. "bits >= 1000"; see
+    /// `action_condition`
+    #[serde(default)]
+    pub condition: Option<String>,
+}
+
+/// Limits for a spec'd pipeline; see `PipelineCommands::set_pipeline_throttle`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PipelineThrottleSpec {
+    pub cooldown_seconds: i32,
+    pub user_cooldown_seconds: i32,
+    pub max_executions: i32,
+    pub max_executions_per_user: i32,
+    pub window_seconds: i32,
+}
+
+fn default_priority() -> i32 {
+    100
+}
+
+fn default_retry_delay_ms() -> i32 {
+    1000
+}
+
+impl PipelineSpec {
+    /// Parse a spec from text. `format` is "toml" or "json".
+    pub fn parse(text: &str, format: &str) -> Result<Self, CommandError> {
+        match format {
+            "toml" => toml::from_str(text)
+                .map_err(|e| CommandError::InvalidInput(format!("Invalid TOML pipeline spec: {}", e))),
+            "json" => serde_json::from_str(text)
+                .map_err(|e| CommandError::InvalidInput(format!("Invalid JSON pipeline spec: {}", e))),
+            other => Err(CommandError::InvalidInput(format!("Unsupported spec format '{}'", other))),
+        }
+    }
+
+    /// Load a spec from disk, choosing the format from the file extension.
+    pub fn from_file(path: &Path) -> Result<Self, CommandError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| CommandError::InvalidInput(format!("Cannot read {}: {}", path.display(), e)))?;
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => "toml",
+            Some("json") => "json",
+            _ => return Err(CommandError::InvalidInput(
+                "Pipeline spec must be a .toml or .json file".to_string()
+            )),
+        };
+        Self::parse(&text, format)
+    }
+}
+
+/// The condition type and JSON config for an action condition as typed:
+/// "previous_success", "previous_failure", "none" (clears it) or an expression
+/// such as "bits >= 1000".
+pub fn action_condition(condition: &str) -> (String, Option<String>) {
+    match condition.trim() {
+        "" | "none" => (String::new(), None),
+        kind @ ("previous_success" | "previous_failure") => (kind.to_string(), None),
+        expression => (
+            "expression".to_string(),
+            Some(serde_json::json!({ "if": expression }).to_string()),
+        ),
+    }
+}
+
+/// Applies one `key=value` throttle setting as typed, e.g. `cooldown=30` or
+/// `max_per_user=2`. Values are seconds or run counts; 0 turns a limit off.
+pub fn apply_throttle_setting(throttle: &mut PipelineThrottle, setting: &str) -> Result<(), CommandError> {
+    let (key, value) = setting.split_once('=')
+        .ok_or_else(|| CommandError::InvalidInput(format!("Expected key=value, got '{}'", setting)))?;
+    let value: i32 = value.trim().trim_end_matches('s').parse()
+        .map_err(|_| CommandError::InvalidInput(format!("'{}' is not a number", value)))?;
+    let field = match key.trim() {
+        "cooldown" => &mut throttle.cooldown_seconds,
+        "user_cooldown" => &mut throttle.user_cooldown_seconds,
+        "max" => &mut throttle.max_executions,
+        "max_per_user" => &mut throttle.max_executions_per_user,
+        "window" => &mut throttle.window_seconds,
+        other => return Err(CommandError::InvalidInput(format!(
+            "Unknown throttle setting '{}' (cooldown, user_cooldown, max, max_per_user, window)", other
+        ))),
+    };
+    *field = value;
+    Ok(())
+}
+
+fn config_to_string(config: &serde_json::Value) -> String {
+    if config.is_null() {
+        "{}".to_string()
+    } else {
+        config.to_string()
+    }
+}
+
 // Command handlers
 pub struct PipelineCommands;
 
@@ -361,7 +565,12 @@ impl PipelineCommands {
         timeout_ms: Option<i32>,
         retry_count: i32,
         retry_delay_ms: i32,
+        condition: Option<&str>,
     ) -> Result<CommandResult<AddActionResult>, CommandError> {
+        let (condition_type, condition_config) = match condition {
+            Some(condition) => action_condition(condition),
+            None => (String::new(), None),
+        };
         let request = AddActionRequest {
             pipeline_id: pipeline_id.to_string(),
             action_type: action_type.to_string(),
@@ -372,6 +581,8 @@ impl PipelineCommands {
             timeout_ms,
             retry_count,
             retry_delay_ms,
+            condition_type: Some(condition_type).filter(|t| !t.is_empty()),
+            condition_config,
         };
 
         let response = client.pipeline.clone()
@@ -400,7 +611,15 @@ impl PipelineCommands {
         timeout_ms: Option<i32>,
         retry_count: Option<i32>,
         retry_delay_ms: Option<i32>,
+        condition: Option<&str>,
     ) -> Result<CommandResult<UpdateActionResult>, CommandError> {
+        let (condition_type, condition_config) = match condition {
+            Some(condition) => {
+                let (kind, config) = action_condition(condition);
+                (Some(kind), config)
+            }
+            None => (None, None),
+        };
         let request = UpdateActionRequest {
             action_id: action_id.to_string(),
             action_config: action_config.map(|s| s.to_string()),
@@ -410,6 +629,8 @@ impl PipelineCommands {
             timeout_ms,
             retry_count,
             retry_delay_ms,
+            condition_type,
+            condition_config,
         };
 
         let response = client.pipeline.clone()
@@ -513,11 +734,13 @@ impl PipelineCommands {
         pipeline_id: Option<&str>,
         limit: Option<i32>,
         offset: Option<i32>,
+        errors_only: bool,
     ) -> Result<CommandResult<GetExecutionHistoryResult>, CommandError> {
         let request = GetExecutionHistoryRequest {
             pipeline_id: pipeline_id.map(|s| s.to_string()),
             limit,
             offset,
+            errors_only,
         };
 
         let response = client.pipeline.clone()
@@ -560,6 +783,249 @@ impl PipelineCommands {
         Ok(CommandResult::new(GetExecutionDetailsResult { execution }))
     }
 
+    /// Accepts either a pipeline UUID or a pipeline name and returns the UUID.
+    pub async fn resolve_pipeline_id(
+        client: &GrpcClient,
+        name_or_id: &str,
+    ) -> Result<String, CommandError> {
+        if uuid::Uuid::parse_str(name_or_id).is_ok() {
+            return Ok(name_or_id.to_string());
+        }
+
+        let result = Self::list_pipelines(client, true).await?;
+        result.data.pipelines
+            .into_iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name_or_id))
+            .map(|p| p.pipeline_id)
+            .ok_or_else(|| CommandError::NotFound(format!("No pipeline named '{}'", name_or_id)))
+    }
+
+    /// Create a pipeline together with all of its filters and actions.
+    pub async fn create_from_spec(
+        client: &GrpcClient,
+        spec: &PipelineSpec,
+    ) -> Result<CommandResult<CreateFromSpecResult>, CommandError> {
+        let created = Self::create_pipeline(
+            client,
+            &spec.name,
+            &spec.description,
+            spec.priority,
+            spec.stop_on_match,
+            spec.stop_on_error,
+            spec.tags.clone(),
+        ).await?;
+        let pipeline_id = created.data.pipeline.pipeline_id.clone();
+
+        let mut warnings = Vec::new();
+        let mut filters_added = 0;
+        for (idx, filter) in spec.filters.iter().enumerate() {
+            match Self::add_filter(
+                client,
+                &pipeline_id,
+                &filter.filter_type,
+                &config_to_string(&filter.config),
+                filter.order.or(Some(idx as i32)),
+                filter.negated,
+                filter.required,
+            ).await {
+                Ok(_) => filters_added += 1,
+                Err(e) => warnings.push(format!("Filter '{}' not added: {}", filter.filter_type, e)),
+            }
+        }
+
+        let mut actions_added = 0;
+        for (idx, action) in spec.actions.iter().enumerate() {
+            match Self::add_action(
+                client,
+                &pipeline_id,
+                &action.action_type,
+                &config_to_string(&action.config),
+                action.order.or(Some(idx as i32)),
+                action.continue_on_error,
+                action.is_async,
+                action.timeout_ms,
+                action.retry_count,
+                action.retry_delay_ms,
+                action.condition.as_deref(),
+            ).await {
+                Ok(_) => actions_added += 1,
+                Err(e) => warnings.push(format!("Action '{}' not added: {}", action.action_type, e)),
+            }
+        }
+
+        if let Some(throttle) = &spec.throttle {
+            let throttle = PipelineThrottle {
+                pipeline_id: pipeline_id.clone(),
+                cooldown_seconds: throttle.cooldown_seconds,
+                user_cooldown_seconds: throttle.user_cooldown_seconds,
+                max_executions: throttle.max_executions,
+                max_executions_per_user: throttle.max_executions_per_user,
+                window_seconds: throttle.window_seconds,
+            };
+            if let Err(e) = Self::set_pipeline_throttle(client, throttle).await {
+                warnings.push(format!("Throttle not set: {}", e));
+            }
+        }
+
+        let mut pipeline = created.data.pipeline;
+        if spec.enabled == Some(false) {
+            Self::toggle_pipeline(client, &pipeline_id, false).await?;
+            pipeline.enabled = false;
+        }
+
+        Ok(CommandResult::with_warnings(
+            CreateFromSpecResult { pipeline, filters_added, actions_added },
+            warnings,
+        ))
+    }
+
+    pub async fn test_fire_pipeline(
+        client: &GrpcClient,
+        pipeline_id: &str,
+        event_type: &str,
+        event_data: &str,
+    ) -> Result<CommandResult<TestFirePipelineResult>, CommandError> {
+        let request = TestFirePipelineRequest {
+            pipeline_id: pipeline_id.to_string(),
+            event_type: event_type.to_string(),
+            event_data: event_data.to_string(),
+        };
+
+        let response = client.pipeline.clone()
+            .test_fire_pipeline(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let inner = response.into_inner();
+
+        // A failed action still produces an execution log worth showing
+        if !inner.success && inner.execution.is_none() {
+            return Err(CommandError::DataError(inner.message));
+        }
+
+        Ok(CommandResult::new(TestFirePipelineResult {
+            filters_passed: inner.filters_passed,
+            succeeded: inner.success,
+            message: inner.message,
+            execution: inner.execution,
+        }))
+    }
+
+    pub async fn get_pipeline_throttle(
+        client: &GrpcClient,
+        pipeline_id: &str,
+    ) -> Result<CommandResult<PipelineThrottleResult>, CommandError> {
+        let request = GetPipelineThrottleRequest {
+            pipeline_id: pipeline_id.to_string(),
+        };
+
+        let response = client.pipeline.clone()
+            .get_pipeline_throttle(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let inner = response.into_inner();
+        if !inner.success {
+            return Err(CommandError::DataError(inner.message));
+        }
+
+        Ok(CommandResult::new(PipelineThrottleResult {
+            throttle: inner.throttle.ok_or_else(|| CommandError::DataError("No throttle returned".to_string()))?,
+            message: inner.message,
+        }))
+    }
+
+    /// Save a pipeline's limits; all zero removes them.
+    pub async fn set_pipeline_throttle(
+        client: &GrpcClient,
+        throttle: PipelineThrottle,
+    ) -> Result<CommandResult<PipelineThrottleResult>, CommandError> {
+        let request = SetPipelineThrottleRequest {
+            throttle: Some(throttle),
+        };
+
+        let response = client.pipeline.clone()
+            .set_pipeline_throttle(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let inner = response.into_inner();
+        if !inner.success {
+            return Err(CommandError::DataError(inner.message));
+        }
+
+        Ok(CommandResult::new(PipelineThrottleResult {
+            throttle: inner.throttle.ok_or_else(|| CommandError::DataError("No throttle returned".to_string()))?,
+            message: inner.message,
+        }))
+    }
+
+    /// Forget the runs a pipeline's limits have counted so far.
+    pub async fn reset_pipeline_throttle(
+        client: &GrpcClient,
+        pipeline_id: &str,
+    ) -> Result<CommandResult<ResetPipelineThrottleResult>, CommandError> {
+        let request = ResetPipelineThrottleRequest {
+            pipeline_id: pipeline_id.to_string(),
+        };
+
+        let response = client.pipeline.clone()
+            .reset_pipeline_throttle(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let inner = response.into_inner();
+        if !inner.success {
+            return Err(CommandError::DataError(inner.message));
+        }
+
+        Ok(CommandResult::new(ResetPipelineThrottleResult {
+            hits_cleared: inner.hits_cleared,
+        }))
+    }
+
+    pub async fn simulate_event(
+        client: &GrpcClient,
+        platform: &str,
+        event_type: &str,
+        overrides_json: &str,
+    ) -> Result<CommandResult<SimulateEventResult>, CommandError> {
+        let request = SimulateEventRequest {
+            platform: platform.to_string(),
+            event_type: event_type.to_string(),
+            overrides_json: overrides_json.to_string(),
+        };
+
+        let response = client.pipeline.clone()
+            .simulate_event(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let inner = response.into_inner();
+        if !inner.success {
+            return Err(CommandError::DataError(inner.message));
+        }
+
+        Ok(CommandResult::new(SimulateEventResult {
+            event_type: inner.event_type,
+            message: inner.message,
+            event_json: inner.event_json,
+        }))
+    }
+
+    pub async fn list_simulated_events(
+        client: &GrpcClient,
+    ) -> Result<CommandResult<ListSimulatedEventsResult>, CommandError> {
+        let response = client.pipeline.clone()
+            .list_simulated_events(ListSimulatedEventsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult::new(ListSimulatedEventsResult {
+            event_types: response.into_inner().event_types,
+        }))
+    }
+
     pub async fn reload_pipelines(
         client: &GrpcClient,
     ) -> Result<CommandResult<ReloadPipelinesResult>, CommandError> {
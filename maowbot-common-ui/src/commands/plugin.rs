@@ -2,8 +2,11 @@ use crate::GrpcClient;
 use super::CommandError;
 use maowbot_proto::maowbot::services::{
     ListPluginsRequest, EnablePluginRequest, DisablePluginRequest, RemovePluginRequest,
-    GetSystemStatusRequest, PluginInfo, plugin_status,
+    GetSystemStatusRequest, GetRuntimeMetricsRequest, plugin_status,
+    RunDiagnosticsRequest, RunDiagnosticsResponse, RunChatLoadTestRequest, ChatLoadTestUpdate,
 };
+use std::collections::HashMap;
+use tonic::Streaming;
 
 /// Result of listing plugins
 pub struct ListPluginsResult {
@@ -55,6 +58,62 @@ pub struct SystemStatusResult {
     pub connected_plugins: Vec<String>,
 }
 
+/// One sample of the server's runtime counters
+#[derive(Debug, Clone)]
+pub struct RuntimeMetricsSample {
+    pub uptime_seconds: i64,
+    pub sampled_at_ms: i64,
+    pub events_total: i64,
+    pub event_counts: HashMap<String, i64>,
+    pub osc_running: bool,
+    pub osc_packets_received: i64,
+    pub osc_packets_sent: i64,
+    pub connections: Vec<ConnectionState>,
+    /// None when the server has no outbound guard
+    pub outbound: Option<OutboundStats>,
+}
+
+/// Outbound chat guard totals since startup
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutboundStats {
+    pub sent: i64,
+    pub duplicates_dropped: i64,
+    pub rate_limited: i64,
+    pub own_messages_ignored: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectionState {
+    pub platform: String,
+    pub account: String,
+    pub connected: bool,
+}
+
+/// Per-second rates between two samples
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricRates {
+    pub events_per_sec: f64,
+    pub osc_in_per_sec: f64,
+    pub osc_out_per_sec: f64,
+}
+
+impl RuntimeMetricsSample {
+    /// Rates since an earlier sample. Returns zeros if the clock did not advance
+    /// or the counters went backwards (server restart).
+    pub fn rates_since(&self, earlier: &RuntimeMetricsSample) -> MetricRates {
+        let elapsed = (self.sampled_at_ms - earlier.sampled_at_ms) as f64 / 1000.0;
+        if elapsed <= 0.0 || self.events_total < earlier.events_total {
+            return MetricRates::default();
+        }
+        let rate = |now: i64, before: i64| (now - before).max(0) as f64 / elapsed;
+        MetricRates {
+            events_per_sec: rate(self.events_total, earlier.events_total),
+            osc_in_per_sec: rate(self.osc_packets_received, earlier.osc_packets_received),
+            osc_out_per_sec: rate(self.osc_packets_sent, earlier.osc_packets_sent),
+        }
+    }
+}
+
 /// Plugin command handlers
 pub struct PluginCommands;
 
@@ -221,4 +280,70 @@ impl PluginCommands {
             connected_plugins,
         })
     }
-}
\ No newline at end of file
+    
+    /// Sample the server's runtime counters (events, OSC packets, connections)
+    pub async fn get_runtime_metrics(
+        client: &GrpcClient,
+        include_event_breakdown: bool,
+    ) -> Result<RuntimeMetricsSample, CommandError> {
+        let request = GetRuntimeMetricsRequest { include_event_breakdown };
+        
+        let mut client = client.plugin.clone();
+        let response = client
+            .get_runtime_metrics(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+        
+        Ok(RuntimeMetricsSample {
+            uptime_seconds: response.uptime_seconds,
+            sampled_at_ms: response.sampled_at_ms,
+            events_total: response.events_total,
+            event_counts: response.event_counts,
+            osc_running: response.osc_running,
+            osc_packets_received: response.osc_packets_received,
+            osc_packets_sent: response.osc_packets_sent,
+            connections: response.connections.into_iter().map(|c| ConnectionState {
+                platform: c.platform,
+                account: c.account,
+                connected: c.connected,
+            }).collect(),
+            outbound: response.outbound.map(|o| OutboundStats {
+                sent: o.sent,
+                duplicates_dropped: o.duplicates_dropped,
+                rate_limited: o.rate_limited,
+                own_messages_ignored: o.own_messages_ignored,
+            }),
+        })
+    }
+    
+    /// Runs the server's dependency checks; `categories` empty runs them all.
+    pub async fn run_diagnostics(
+        client: &GrpcClient,
+        categories: Vec<String>,
+    ) -> Result<RunDiagnosticsResponse, CommandError> {
+        let request = RunDiagnosticsRequest { categories };
+        
+        let mut client = client.plugin.clone();
+        let response = client
+            .run_diagnostics(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        
+        Ok(response.into_inner())
+    }
+    
+    /// Starts a chat load test; the stream has progress updates and ends with the report.
+    pub async fn run_chat_load_test(
+        client: &GrpcClient,
+        request: RunChatLoadTestRequest,
+    ) -> Result<Streaming<ChatLoadTestUpdate>, CommandError> {
+        let mut client = client.plugin.clone();
+        let response = client
+            .run_chat_load_test(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        
+        Ok(response.into_inner())
+    }
+}
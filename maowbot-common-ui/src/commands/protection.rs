@@ -0,0 +1,85 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    ProtectionIncident, GetProtectionStatusRequest, GetProtectionStatusResponse,
+    SetShieldModeRequest, StartLockdownRequest, EndLockdownRequest,
+    ListIncidentsRequest, GetIncidentRequest,
+};
+
+/// Raid defense command handlers
+pub struct ProtectionCommands;
+
+impl ProtectionCommands {
+    pub async fn status(client: &GrpcClient) -> Result<GetProtectionStatusResponse, CommandError> {
+        let mut protection_client = client.protection.clone();
+        let response = protection_client
+            .get_protection_status(GetProtectionStatusRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    pub async fn set_shield_mode(client: &GrpcClient, active: bool) -> Result<(), CommandError> {
+        let mut protection_client = client.protection.clone();
+        protection_client
+            .set_shield_mode(SetShieldModeRequest { active })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Lock chat down by hand
+    pub async fn start_lockdown(
+        client: &GrpcClient,
+        reason: &str,
+    ) -> Result<ProtectionIncident, CommandError> {
+        let mut protection_client = client.protection.clone();
+        protection_client
+            .start_lockdown(StartLockdownRequest { reason: reason.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .incident
+            .ok_or_else(|| CommandError::DataError("Server returned no incident".to_string()))
+    }
+
+    /// Lift the running lockdown and restore chat settings
+    pub async fn end_lockdown(client: &GrpcClient) -> Result<ProtectionIncident, CommandError> {
+        let mut protection_client = client.protection.clone();
+        protection_client
+            .end_lockdown(EndLockdownRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .incident
+            .ok_or_else(|| CommandError::DataError("Server returned no incident".to_string()))
+    }
+
+    /// Newest first, without captured messages
+    pub async fn list_incidents(
+        client: &GrpcClient,
+        limit: i32,
+    ) -> Result<Vec<ProtectionIncident>, CommandError> {
+        let mut protection_client = client.protection.clone();
+        let response = protection_client
+            .list_incidents(ListIncidentsRequest { limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().incidents)
+    }
+
+    /// One incident with its captured messages
+    pub async fn get_incident(
+        client: &GrpcClient,
+        incident_id: &str,
+    ) -> Result<ProtectionIncident, CommandError> {
+        let mut protection_client = client.protection.clone();
+        protection_client
+            .get_incident(GetIncidentRequest { incident_id: incident_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .incident
+            .ok_or_else(|| CommandError::DataError("Server returned no incident".to_string()))
+    }
+}
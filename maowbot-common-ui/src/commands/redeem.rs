@@ -1,11 +1,14 @@
 use crate::{GrpcClient, CommandResult, CommandError};
+use super::MAX_PAGE_SIZE;
 use maowbot_proto::maowbot::services::{
     CreateRedeemRequest, GetRedeemRequest, UpdateRedeemRequest,
     DeleteRedeemRequest, ListRedeemsRequest, ExecuteRedeemRequest,
-    GetRedeemUsageRequest, SyncRedeemsRequest, RedeemInfo,
+    SyncRedeemsRequest, SyncDirection, SyncResult, RedeemInfo,
+    ListRedeemSchedulesRequest, ListRedeemSchedulesResponse, AddRedeemScheduleRequest, RedeemScheduleInfo,
+    RemoveRedeemScheduleRequest, SetRedeemScheduleEnabledRequest,
+    ListRedeemApprovalsRequest, DecideRedeemRequest, RedeemApproval,
 };
 use maowbot_proto::maowbot::common::{Redeem, PageRequest};
-use uuid::Uuid;
 
 // Result structures
 pub struct CreateRedeemResult {
@@ -23,6 +26,8 @@ pub struct UpdateRedeemResult {
 pub struct ListRedeemsResult {
     pub redeems: Vec<RedeemInfo>,
     pub total_count: i32,
+    /// Empty on the last page
+    pub next_page_token: String,
 }
 
 pub struct ExecuteRedeemResult {
@@ -39,6 +44,9 @@ pub struct SyncRedeemsResult {
     pub added_count: i32,
     pub updated_count: i32,
     pub removed_count: i32,
+    pub error_count: i32,
+    /// Every planned change, applied or not
+    pub changes: Vec<SyncResult>,
 }
 
 // Command handlers
@@ -196,6 +204,32 @@ impl RedeemCommands {
         platform: Option<&str>,
         active_only: bool,
         page_size: i32,
+    ) -> Result<CommandResult<ListRedeemsResult>, CommandError> {
+        Self::list_redeems_page(client, platform, active_only, page_size, String::new()).await
+    }
+
+    /// Every redeem on the platform, following page tokens to the end.
+    pub async fn list_all_redeems(
+        client: &GrpcClient,
+        platform: Option<&str>,
+        active_only: bool,
+    ) -> Result<CommandResult<ListRedeemsResult>, CommandError> {
+        let mut all = Self::list_redeems_page(client, platform, active_only, MAX_PAGE_SIZE, String::new()).await?;
+        while !all.data.next_page_token.is_empty() {
+            let token = std::mem::take(&mut all.data.next_page_token);
+            let page = Self::list_redeems_page(client, platform, active_only, MAX_PAGE_SIZE, token).await?;
+            all.data.redeems.extend(page.data.redeems);
+            all.data.next_page_token = page.data.next_page_token;
+        }
+        Ok(all)
+    }
+
+    async fn list_redeems_page(
+        client: &GrpcClient,
+        platform: Option<&str>,
+        active_only: bool,
+        page_size: i32,
+        page_token: String,
     ) -> Result<CommandResult<ListRedeemsResult>, CommandError> {
         let request = ListRedeemsRequest {
             platform: platform.unwrap_or_default().to_string(),
@@ -203,8 +237,12 @@ impl RedeemCommands {
             dynamic_only: false,
             page: Some(PageRequest {
                 page_size,
-                page_token: String::new(),
+                page_token,
             }),
+            order_by: String::new(),
+            descending: false,
+            read_mask: None,
+            name_prefix: String::new(),
         };
 
         let response = client.redeem.clone()
@@ -216,11 +254,13 @@ impl RedeemCommands {
         let total_count = resp.page.as_ref()
             .map(|p| p.total_count)
             .unwrap_or(resp.redeems.len() as i32);
+        let next_page_token = resp.page.map(|p| p.next_page_token).unwrap_or_default();
 
         Ok(CommandResult {
             data: ListRedeemsResult {
                 redeems: resp.redeems,
                 total_count,
+                next_page_token,
             },
             warnings: vec![],
         })
@@ -257,14 +297,18 @@ impl RedeemCommands {
         })
     }
 
+    /// Compares the bot's redeems with Twitch's rewards. `direction` is a
+    /// `SyncDirection` value; with `dry_run` nothing is changed.
     pub async fn sync_redeems(
         client: &GrpcClient,
         platform: &str,
+        direction: SyncDirection,
+        dry_run: bool,
     ) -> Result<CommandResult<SyncRedeemsResult>, CommandError> {
         let request = SyncRedeemsRequest {
             platforms: vec![platform.to_string()],
-            direction: 3, // SYNC_DIRECTION_BIDIRECTIONAL
-            dry_run: false,
+            direction: direction as i32,
+            dry_run,
         };
 
         let response = client.redeem.clone()
@@ -279,6 +323,8 @@ impl RedeemCommands {
                 added_count: resp.created_count,
                 updated_count: resp.updated_count,
                 removed_count: resp.deleted_count,
+                error_count: resp.error_count,
+                changes: resp.results,
             },
             warnings: vec![],
         })
@@ -393,4 +439,106 @@ impl RedeemCommands {
             Err(CommandError::DataError(format!("Redeem '{}' not found on platform '{}'", redeem_name, platform)))
         }
     }
-}
\ No newline at end of file
+
+    /// Whether redemptions of the redeem wait in the approval queue.
+    pub async fn update_requires_approval(
+        client: &GrpcClient,
+        platform: &str,
+        redeem_name: &str,
+        requires_approval: bool,
+    ) -> Result<CommandResult<UpdateRedeemResult>, CommandError> {
+        if let Some(mut redeem) = Self::find_redeem_by_name(client, platform, redeem_name).await? {
+            let redeem_id = redeem.redeem_id.clone();
+            redeem.metadata.insert("requires_approval".to_string(), requires_approval.to_string());
+            Self::update_redeem(client, &redeem_id, redeem).await
+        } else {
+            Err(CommandError::DataError(format!("Redeem '{}' not found on platform '{}'", redeem_name, platform)))
+        }
+    }
+
+    /// Redemptions waiting for approval, oldest first.
+    pub async fn list_approvals(client: &GrpcClient) -> Result<Vec<RedeemApproval>, CommandError> {
+        let response = client.redeem.clone()
+            .list_redeem_approvals(ListRedeemApprovalsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().approvals)
+    }
+
+    /// Runs a queued redemption.
+    pub async fn approve(client: &GrpcClient, approval_id: &str) -> Result<RedeemApproval, CommandError> {
+        let response = client.redeem.clone()
+            .approve_redeem(DecideRedeemRequest { approval_id: approval_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Drops a queued redemption and refunds the viewer's points.
+    pub async fn deny(client: &GrpcClient, approval_id: &str) -> Result<RedeemApproval, CommandError> {
+        let response = client.redeem.clone()
+            .deny_redeem(DecideRedeemRequest { approval_id: approval_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Every scheduled redeem with its schedules and current cost.
+    pub async fn list_schedules(client: &GrpcClient) -> Result<ListRedeemSchedulesResponse, CommandError> {
+        let response = client.redeem.clone()
+            .list_redeem_schedules(ListRedeemSchedulesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// `amount` is the cost for "set_cost" and the factor for "multiply_cost".
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_schedule(
+        client: &GrpcClient,
+        redeem_id: &str,
+        name: &str,
+        condition: &str,
+        condition_value: &str,
+        action: &str,
+        amount: f64,
+        priority: i32,
+    ) -> Result<RedeemScheduleInfo, CommandError> {
+        let response = client.redeem.clone()
+            .add_redeem_schedule(AddRedeemScheduleRequest {
+                redeem_id: redeem_id.to_string(),
+                name: name.to_string(),
+                condition: condition.to_string(),
+                condition_value: condition_value.to_string(),
+                action: action.to_string(),
+                amount,
+                priority,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    pub async fn remove_schedule(client: &GrpcClient, schedule_id: &str) -> Result<(), CommandError> {
+        client.redeem.clone()
+            .remove_redeem_schedule(RemoveRedeemScheduleRequest { schedule_id: schedule_id.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn set_schedule_enabled(
+        client: &GrpcClient,
+        schedule_id: &str,
+        enabled: bool,
+    ) -> Result<RedeemScheduleInfo, CommandError> {
+        let response = client.redeem.clone()
+            .set_redeem_schedule_enabled(SetRedeemScheduleEnabledRequest {
+                schedule_id: schedule_id.to_string(),
+                enabled,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    ChatResponder, ResponderHit, ListRespondersRequest, AddResponderRequest, UpdateResponderRequest,
+    DeleteResponderRequest, TestRespondersRequest,
+};
+
+/// Chat responder command handlers
+pub struct ResponderCommands;
+
+impl ResponderCommands {
+    pub async fn list_responders(client: &GrpcClient) -> Result<Vec<ChatResponder>, CommandError> {
+        let mut responders_client = client.responders.clone();
+        let response = responders_client
+            .list_responders(ListRespondersRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().responders)
+    }
+
+    pub async fn add_responder(client: &GrpcClient, request: AddResponderRequest) -> Result<ChatResponder, CommandError> {
+        let mut responders_client = client.responders.clone();
+        responders_client
+            .add_responder(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .responder
+            .ok_or_else(|| CommandError::DataError("Server returned no responder".to_string()))
+    }
+
+    /// Change a responder; unset fields stay as they are
+    pub async fn update_responder(client: &GrpcClient, request: UpdateResponderRequest) -> Result<ChatResponder, CommandError> {
+        let mut responders_client = client.responders.clone();
+        responders_client
+            .update_responder(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner()
+            .responder
+            .ok_or_else(|| CommandError::DataError("Server returned no responder".to_string()))
+    }
+
+    pub async fn delete_responder(client: &GrpcClient, name: &str) -> Result<(), CommandError> {
+        let mut responders_client = client.responders.clone();
+        responders_client
+            .delete_responder(DeleteResponderRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Which responders a message would trigger, ignoring chance and cooldowns
+    pub async fn test_message(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+        text: &str,
+    ) -> Result<Vec<ResponderHit>, CommandError> {
+        let mut responders_client = client.responders.clone();
+        let response = responders_client
+            .test_responders(TestRespondersRequest {
+                text: text.to_string(),
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+                user: String::new(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().hits)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    GetSessionRequest, GetSessionResponse, ListSessionsRequest, StreamSession,
+};
+
+/// Stream session query handlers
+pub struct StreamSessionCommands;
+
+impl StreamSessionCommands {
+    /// Newest sessions first; `limit` 0 uses the server default.
+    pub async fn list_sessions(client: &GrpcClient, limit: i32) -> Result<Vec<StreamSession>, CommandError> {
+        let mut sessions_client = client.sessions.clone();
+        let response = sessions_client
+            .list_sessions(ListSessionsRequest { limit })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().sessions)
+    }
+
+    /// One session by id, or "current"/"last", with its markers and top chatters.
+    pub async fn get_session(
+        client: &GrpcClient,
+        session: &str,
+        top_chatters: i32,
+    ) -> Result<GetSessionResponse, CommandError> {
+        let mut sessions_client = client.sessions.clone();
+        let response = sessions_client
+            .get_session(GetSessionRequest {
+                session: session.to_string(),
+                top_chatters,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+}
@@ -0,0 +1,144 @@
+use crate::GrpcClient;
+use super::{CommandError, to_datetime};
+use maowbot_proto::maowbot::services::{
+    ApiToken, ListApiTokensRequest, CreateApiTokenRequest, RevokeApiTokenRequest, ListAuditLogRequest,
+    AuditLogEntry,
+};
+
+/// An API token as shown to the user (never includes the secret)
+pub struct TokenInfo {
+    pub token_id: String,
+    pub name: String,
+    pub role: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The workspace the token is limited to; empty for every workspace
+    pub workspace: String,
+}
+
+impl From<ApiToken> for TokenInfo {
+    fn from(t: ApiToken) -> Self {
+        Self {
+            token_id: t.token_id,
+            name: t.name,
+            role: t.role,
+            created_at: to_datetime(t.created_at),
+            last_used_at: to_datetime(t.last_used_at),
+            revoked_at: to_datetime(t.revoked_at),
+            workspace: t.workspace,
+        }
+    }
+}
+
+/// A newly created token with its secret, which the server only returns once
+pub struct CreatedToken {
+    pub token: TokenInfo,
+    pub secret: String,
+}
+
+/// One gRPC audit record
+pub struct AuditInfo {
+    pub client_name: String,
+    pub role: String,
+    pub method: String,
+    pub workspace: String,
+    pub allowed: bool,
+    pub reason: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// What the call changed, e.g. "config:chat.prefix"; empty if not noted
+    pub target: String,
+    /// JSON, empty if not recorded
+    pub before_value: String,
+    pub after_value: String,
+    /// "Ok" or a gRPC code; empty for denied calls
+    pub outcome: String,
+}
+
+impl From<AuditLogEntry> for AuditInfo {
+    fn from(e: AuditLogEntry) -> Self {
+        Self {
+            client_name: e.client_name,
+            role: e.role,
+            method: e.method,
+            workspace: e.workspace,
+            allowed: e.allowed,
+            reason: e.reason,
+            created_at: to_datetime(e.created_at),
+            target: e.target,
+            before_value: e.before_value,
+            after_value: e.after_value,
+            outcome: e.outcome,
+        }
+    }
+}
+
+/// API token and audit log command handlers
+pub struct TokenCommands;
+
+impl TokenCommands {
+    /// List API tokens, optionally including revoked ones
+    pub async fn list_tokens(
+        client: &GrpcClient,
+        include_revoked: bool,
+    ) -> Result<Vec<TokenInfo>, CommandError> {
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .list_api_tokens(ListApiTokensRequest { include_revoked })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(response.into_inner().tokens.into_iter().map(TokenInfo::from).collect())
+    }
+
+    /// Create a token bound to `role` ("admin", "moderator" or "readonly"),
+    /// optionally limited to one workspace
+    pub async fn create_token(
+        client: &GrpcClient,
+        name: &str,
+        role: &str,
+        workspace: Option<&str>,
+    ) -> Result<CreatedToken, CommandError> {
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .create_api_token(CreateApiTokenRequest {
+                name: name.to_string(),
+                role: role.to_string(),
+                workspace: workspace.unwrap_or("").to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?
+            .into_inner();
+
+        let token = response.token
+            .ok_or_else(|| CommandError::DataError("Server returned no token".to_string()))?;
+        Ok(CreatedToken { token: token.into(), secret: response.secret })
+    }
+
+    /// Revoke a token by name; clients using it are rejected from then on
+    pub async fn revoke_token(
+        client: &GrpcClient,
+        name: &str,
+    ) -> Result<(), CommandError> {
+        let mut config_client = client.config.clone();
+        config_client
+            .revoke_api_token(RevokeApiTokenRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Most recent audit log entries first
+    pub async fn audit_log(
+        client: &GrpcClient,
+        limit: i32,
+    ) -> Result<Vec<AuditInfo>, CommandError> {
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .list_audit_log(ListAuditLogRequest { limit, ..Default::default() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(response.into_inner().entries.into_iter().map(AuditInfo::from).collect())
+    }
+}
@@ -4,6 +4,12 @@ use maowbot_proto::maowbot::services::{
     GetJoinedChannelsRequest, ChannelMembership,
     GetChannelInfoRequest, GetStreamInfoRequest,
     GetFollowAgeRequest, StreamInfo, ChannelInfo,
+    CreateStreamMarkerRequest, StreamMarker,
+    ListVodChaptersRequest, GetVodChaptersRequest, VodChapters,
+    ListScheduleSegmentsRequest, CreateScheduleSegmentRequest, UpdateScheduleSegmentRequest,
+    DeleteScheduleSegmentRequest, ScheduleSegment,
+    ListEventSubSubscriptionsRequest, ListEventSubSubscriptionsResponse,
+    RepairEventSubSubscriptionsRequest, RepairEventSubSubscriptionsResponse,
 };
 
 // Result structures
@@ -30,6 +36,26 @@ pub struct GetFollowAgeResult {
     pub follow_duration: String,
 }
 
+pub struct CreateStreamMarkerResult {
+    pub marker: Option<StreamMarker>,
+}
+
+pub struct ListVodChaptersResult {
+    pub chapters: Vec<VodChapters>,
+}
+
+pub struct GetVodChaptersResult {
+    pub chapters: Option<VodChapters>,
+}
+
+pub struct ListScheduleSegmentsResult {
+    pub segments: Vec<ScheduleSegment>,
+}
+
+pub struct ScheduleSegmentResult {
+    pub segment: Option<ScheduleSegment>,
+}
+
 // Command handlers
 pub struct TwitchCommands;
 
@@ -194,4 +220,192 @@ impl TwitchCommands {
             warnings: vec![],
         })
     }
-}
\ No newline at end of file
+
+    pub async fn create_stream_marker(
+        client: &GrpcClient,
+        description: &str,
+    ) -> Result<CommandResult<CreateStreamMarkerResult>, CommandError> {
+        let request = CreateStreamMarkerRequest {
+            description: description.to_string(),
+        };
+
+        let response = client.twitch.clone()
+            .create_stream_marker(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: CreateStreamMarkerResult {
+                marker: response.into_inner().marker,
+            },
+            warnings: vec![],
+        })
+    }
+
+    pub async fn list_vod_chapters(
+        client: &GrpcClient,
+        limit: i32,
+    ) -> Result<CommandResult<ListVodChaptersResult>, CommandError> {
+        let request = ListVodChaptersRequest { limit };
+
+        let response = client.twitch.clone()
+            .list_vod_chapters(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: ListVodChaptersResult {
+                chapters: response.into_inner().chapters,
+            },
+            warnings: vec![],
+        })
+    }
+
+    pub async fn get_vod_chapters(
+        client: &GrpcClient,
+        id: &str,
+    ) -> Result<CommandResult<GetVodChaptersResult>, CommandError> {
+        let request = GetVodChaptersRequest {
+            id: id.to_string(),
+        };
+
+        let response = client.twitch.clone()
+            .get_vod_chapters(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: GetVodChaptersResult {
+                chapters: response.into_inner().chapters,
+            },
+            warnings: vec![],
+        })
+    }
+
+    pub async fn list_schedule_segments(
+        client: &GrpcClient,
+        refresh: bool,
+    ) -> Result<CommandResult<ListScheduleSegmentsResult>, CommandError> {
+        let request = ListScheduleSegmentsRequest { refresh };
+
+        let response = client.twitch.clone()
+            .list_schedule_segments(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: ListScheduleSegmentsResult {
+                segments: response.into_inner().segments,
+            },
+            warnings: vec![],
+        })
+    }
+
+    /// `category` is a Twitch category name; empty for none.
+    pub async fn create_schedule_segment(
+        client: &GrpcClient,
+        start_time: maowbot_proto::prost_types::Timestamp,
+        duration_minutes: i32,
+        title: &str,
+        category: &str,
+        is_recurring: bool,
+    ) -> Result<CommandResult<ScheduleSegmentResult>, CommandError> {
+        let request = CreateScheduleSegmentRequest {
+            start_time: Some(start_time),
+            duration_minutes,
+            title: title.to_string(),
+            category: category.to_string(),
+            is_recurring,
+            timezone: String::new(),
+        };
+
+        let response = client.twitch.clone()
+            .create_schedule_segment(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: ScheduleSegmentResult {
+                segment: response.into_inner().segment,
+            },
+            warnings: vec![],
+        })
+    }
+
+    /// Changes the `fields` of `segment` (start_time, duration_minutes, title,
+    /// category, is_canceled) on the segment `segment_id`.
+    pub async fn update_schedule_segment(
+        client: &GrpcClient,
+        segment_id: &str,
+        segment: ScheduleSegment,
+        fields: &[&str],
+    ) -> Result<CommandResult<ScheduleSegmentResult>, CommandError> {
+        let request = UpdateScheduleSegmentRequest {
+            segment_id: segment_id.to_string(),
+            segment: Some(segment),
+            update_mask: Some(maowbot_proto::prost_types::FieldMask {
+                paths: fields.iter().map(|f| f.to_string()).collect(),
+            }),
+        };
+
+        let response = client.twitch.clone()
+            .update_schedule_segment(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: ScheduleSegmentResult {
+                segment: response.into_inner().segment,
+            },
+            warnings: vec![],
+        })
+    }
+
+    pub async fn delete_schedule_segment(
+        client: &GrpcClient,
+        segment_id: &str,
+    ) -> Result<CommandResult<()>, CommandError> {
+        let request = DeleteScheduleSegmentRequest {
+            segment_id: segment_id.to_string(),
+        };
+
+        client.twitch.clone()
+            .delete_schedule_segment(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: (),
+            warnings: vec![],
+        })
+    }
+
+    pub async fn list_eventsub_subscriptions(
+        client: &GrpcClient,
+    ) -> Result<CommandResult<ListEventSubSubscriptionsResponse>, CommandError> {
+        let response = client.twitch.clone()
+            .list_event_sub_subscriptions(ListEventSubSubscriptionsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: response.into_inner(),
+            warnings: vec![],
+        })
+    }
+
+    /// Deletes stale EventSub subscriptions and creates missing ones.
+    pub async fn repair_eventsub_subscriptions(
+        client: &GrpcClient,
+    ) -> Result<CommandResult<RepairEventSubSubscriptionsResponse>, CommandError> {
+        let response = client.twitch.clone()
+            .repair_event_sub_subscriptions(RepairEventSubSubscriptionsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(CommandResult {
+            data: response.into_inner(),
+            warnings: vec![],
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::GrpcClient;
-use super::{CommandResult, CommandError};
+use super::{CommandResult, CommandError, MAX_PAGE_SIZE};
 use maowbot_proto::maowbot::services::{
     CreateUserRequest, DeleteUserRequest, UpdateUserRequest, GetUserRequest,
     SearchUsersRequest, ListUsersRequest, GetPlatformIdentitiesRequest,
@@ -179,6 +179,7 @@ impl UserCommands {
                 page_size: limit,
                 page_token: String::new(),
             }),
+            read_mask: None,
         };
         
         match client.user.clone().search_users(request).await {
@@ -214,9 +215,11 @@ impl UserCommands {
                 active_only,
                 platforms: vec![],
                 roles: vec![],
+                name_prefix: String::new(),
             }),
             order_by: "created_at".to_string(),
             descending: false,
+            read_mask: None,
         };
         
         match client.user.clone().list_users(request).await {
@@ -235,6 +238,24 @@ impl UserCommands {
         }
     }
     
+    /// The first `count` users, following page tokens as needed.
+    pub async fn list_users_upto(
+        client: &GrpcClient,
+        count: usize,
+        active_only: bool,
+    ) -> Result<CommandResult<ListUsersResult>, CommandError> {
+        let page_size = |fetched: usize| (count - fetched).clamp(1, MAX_PAGE_SIZE as usize) as i32;
+        let mut all = Self::list_users(client, page_size(0), None, active_only).await?;
+        while all.data.has_more && all.data.users.len() < count {
+            let token = std::mem::take(&mut all.data.next_page_token);
+            let page = Self::list_users(client, page_size(all.data.users.len()), Some(token), active_only).await?;
+            all.data.users.extend(page.data.users);
+            all.data.has_more = page.data.has_more;
+            all.data.next_page_token = page.data.next_page_token;
+        }
+        Ok(all)
+    }
+    
     // Helper methods
     async fn find_user_by_name(client: &GrpcClient, username: &str) -> Result<Option<ProtoUser>, CommandError> {
         let result = Self::search_users(client, username, 1).await?;
@@ -3,8 +3,13 @@ use super::CommandError;
 use maowbot_proto::maowbot::services::{
     GetCurrentWorldRequest, GetCurrentAvatarRequest, ChangeAvatarRequest,
     GetCurrentInstanceRequest, ListCredentialsRequest, SetConfigRequest,
+    ListVrChatSessionsRequest, CheckVrChatSessionsRequest, VrChatSession,
+    GetVrChatPresenceRequest, GetVrChatPresenceResponse,
+    ListVrChatGroupRequestsRequest, ListVrChatGroupRequestsResponse,
+    RespondVrChatGroupRequestRequest, RespondVrChatGroupRequestResponse,
+    PostVrChatGroupAnnouncementRequest,
 };
-use maowbot_proto::maowbot::common::Platform;
+use maowbot_proto::maowbot::common::{Platform, PlatformCredential};
 
 /// VRChat world information
 pub struct VRChatWorldInfo {
@@ -156,32 +161,7 @@ impl VRChatCommands {
         account_name: &str,
     ) -> Result<(), CommandError> {
         // Verify we have a VRChat credential for this account
-        let request = ListCredentialsRequest {
-            platforms: vec![Platform::Vrchat as i32],
-            active_only: true,
-            include_expired: false,
-            page: None,
-        };
-        
-        let mut cred_client = client.credential.clone();
-        let response = cred_client
-            .list_credentials(request)
-            .await
-            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
-            
-        let credentials = response.into_inner().credentials;
-        let found = credentials
-            .iter()
-            .any(|c| c.credential.as_ref()
-                .map(|cred| cred.user_name.eq_ignore_ascii_case(account_name))
-                .unwrap_or(false));
-            
-        if !found {
-            return Err(CommandError::NotFound(format!(
-                "No VRChat credential found with user_name='{}'. Try 'account add vrchat' first.",
-                account_name
-            )));
-        }
+        Self::find_credential(client, Some(account_name)).await?;
         
         // Store in config
         let config_request = SetConfigRequest {
@@ -199,4 +179,128 @@ impl VRChatCommands {
             
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// The stored VRChat credential for `account_name`, or the only one when
+    /// no name is given. Expired credentials are included so they can be
+    /// logged back in.
+    pub async fn find_credential(
+        client: &GrpcClient,
+        account_name: Option<&str>,
+    ) -> Result<PlatformCredential, CommandError> {
+        let request = ListCredentialsRequest {
+            platforms: vec![Platform::Vrchat as i32],
+            active_only: false,
+            include_expired: true,
+            page: None,
+        };
+
+        let mut cred_client = client.credential.clone();
+        let response = cred_client
+            .list_credentials(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let mut credentials: Vec<PlatformCredential> = response.into_inner().credentials
+            .into_iter()
+            .filter_map(|c| c.credential)
+            .collect();
+        match account_name {
+            Some(name) => credentials
+                .into_iter()
+                .find(|cred| cred.user_name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| CommandError::NotFound(format!(
+                    "No VRChat credential found with user_name='{}'. Try 'account add vrchat' first.",
+                    name
+                ))),
+            None if credentials.len() == 1 => Ok(credentials.remove(0)),
+            None if credentials.is_empty() => Err(CommandError::NotFound(
+                "No VRChat credentials. Try 'account add vrchat' first.".to_string()
+            )),
+            None => Err(CommandError::InvalidInput(
+                "Several VRChat accounts are stored; name one".to_string()
+            )),
+        }
+    }
+
+    /// Results of the last VRChat session check, per account
+    pub async fn list_sessions(client: &GrpcClient) -> Result<Vec<VrChatSession>, CommandError> {
+        let mut vrchat_client = client.vrchat.clone();
+        let response = vrchat_client
+            .list_vr_chat_sessions(ListVrChatSessionsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().sessions)
+    }
+
+    /// Checks every VRChat session now
+    pub async fn check_sessions(client: &GrpcClient) -> Result<Vec<VrChatSession>, CommandError> {
+        let mut vrchat_client = client.vrchat.clone();
+        let response = vrchat_client
+            .check_vr_chat_sessions(CheckVrChatSessionsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().sessions)
+    }
+
+    /// Friends in the streamer's instance; `refresh` polls VRChat first
+    pub async fn get_presence(
+        client: &GrpcClient,
+        refresh: bool,
+    ) -> Result<GetVrChatPresenceResponse, CommandError> {
+        let mut vrchat_client = client.vrchat.clone();
+        let response = vrchat_client
+            .get_vr_chat_presence(GetVrChatPresenceRequest { refresh })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Pending join requests for the VRChat group; `refresh` asks VRChat first
+    pub async fn list_group_requests(
+        client: &GrpcClient,
+        refresh: bool,
+    ) -> Result<ListVrChatGroupRequestsResponse, CommandError> {
+        let mut vrchat_client = client.vrchat.clone();
+        let response = vrchat_client
+            .list_vr_chat_group_requests(ListVrChatGroupRequestsRequest { refresh })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Approves (`accept`) or rejects a join request by usr_ id or display name
+    pub async fn respond_group_request(
+        client: &GrpcClient,
+        user: &str,
+        accept: bool,
+    ) -> Result<RespondVrChatGroupRequestResponse, CommandError> {
+        let mut vrchat_client = client.vrchat.clone();
+        let response = vrchat_client
+            .respond_vr_chat_group_request(RespondVrChatGroupRequestRequest {
+                user: user.to_string(),
+                accept,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner())
+    }
+
+    /// Posts an announcement to the VRChat group, returning the post id
+    pub async fn post_group_announcement(
+        client: &GrpcClient,
+        title: &str,
+        text: &str,
+        notify: bool,
+    ) -> Result<String, CommandError> {
+        let mut vrchat_client = client.vrchat.clone();
+        let response = vrchat_client
+            .post_vr_chat_group_announcement(PostVrChatGroupAnnouncementRequest {
+                title: title.to_string(),
+                text: text.to_string(),
+                notify,
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(response.into_inner().post_id)
+    }
+}
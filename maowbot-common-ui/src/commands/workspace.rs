@@ -0,0 +1,175 @@
+use crate::GrpcClient;
+use super::CommandError;
+use maowbot_proto::maowbot::services::{
+    ListWorkspacesRequest, CreateWorkspaceRequest, DeleteWorkspaceRequest,
+    ListWorkspaceChannelsRequest, AssignWorkspaceChannelRequest, UnassignWorkspaceChannelRequest,
+};
+
+/// A workspace as shown to the user
+pub struct WorkspaceInfo {
+    pub workspace_id: String,
+    pub name: String,
+    pub display_name: String,
+    pub is_default: bool,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Result of listing workspaces
+pub struct ListWorkspacesResult {
+    pub workspaces: Vec<WorkspaceInfo>,
+    /// The workspace this client currently sends requests to
+    pub current: Option<String>,
+}
+
+/// A chat channel answered by a workspace's commands and redeems
+pub struct WorkspaceChannelInfo {
+    pub workspace: String,
+    pub platform: String,
+    pub channel: String,
+}
+
+/// Workspace command handlers
+pub struct WorkspaceCommands;
+
+impl WorkspaceCommands {
+    /// List all workspaces on the server
+    pub async fn list_workspaces(
+        client: &GrpcClient,
+    ) -> Result<ListWorkspacesResult, CommandError> {
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .list_workspaces(ListWorkspacesRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let workspaces = response.into_inner().workspaces.into_iter().map(|ws| WorkspaceInfo {
+            workspace_id: ws.workspace_id,
+            name: ws.name,
+            display_name: ws.display_name,
+            is_default: ws.is_default,
+            created_at: ws.created_at.and_then(|ts| {
+                chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)
+            }),
+        }).collect();
+
+        Ok(ListWorkspacesResult { workspaces, current: client.workspace() })
+    }
+
+    /// Create a new, empty workspace
+    pub async fn create_workspace(
+        client: &GrpcClient,
+        name: &str,
+        display_name: Option<&str>,
+    ) -> Result<WorkspaceInfo, CommandError> {
+        let request = CreateWorkspaceRequest {
+            name: name.to_string(),
+            display_name: display_name.unwrap_or("").to_string(),
+        };
+
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .create_workspace(request)
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        let ws = response.into_inner().workspace
+            .ok_or_else(|| CommandError::DataError("Server returned no workspace".to_string()))?;
+        Ok(WorkspaceInfo {
+            workspace_id: ws.workspace_id,
+            name: ws.name,
+            display_name: ws.display_name,
+            is_default: ws.is_default,
+            created_at: ws.created_at.and_then(|ts| {
+                chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32)
+            }),
+        })
+    }
+
+    /// Delete a workspace with its commands, redeems and config. The server
+    /// refuses while the workspace still has credentials.
+    pub async fn delete_workspace(
+        client: &GrpcClient,
+        name: &str,
+    ) -> Result<(), CommandError> {
+        let mut config_client = client.config.clone();
+        config_client
+            .delete_workspace(DeleteWorkspaceRequest { name: name.to_string() })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        // Don't keep sending requests to a workspace that no longer exists
+        if client.workspace().as_deref() == Some(name) {
+            client.set_workspace(None).map_err(CommandError::InvalidInput)?;
+        }
+        Ok(())
+    }
+
+    /// Switch this client to `name` after checking that it exists.
+    /// "default" clears the selection.
+    pub async fn use_workspace(
+        client: &GrpcClient,
+        name: &str,
+    ) -> Result<WorkspaceInfo, CommandError> {
+        let listed = Self::list_workspaces(client).await?;
+        let ws = listed.workspaces.into_iter()
+            .find(|ws| ws.name.eq_ignore_ascii_case(name) || ws.workspace_id == name)
+            .ok_or_else(|| CommandError::NotFound(format!("Workspace '{}' not found", name)))?;
+
+        let selection = if ws.is_default { None } else { Some(ws.name.as_str()) };
+        client.set_workspace(selection).map_err(CommandError::InvalidInput)?;
+        Ok(ws)
+    }
+
+    /// Channels routed to a workspace; every other channel uses the default one
+    pub async fn list_channels(
+        client: &GrpcClient,
+    ) -> Result<Vec<WorkspaceChannelInfo>, CommandError> {
+        let mut config_client = client.config.clone();
+        let response = config_client
+            .list_workspace_channels(ListWorkspaceChannelsRequest {})
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+
+        Ok(response.into_inner().channels.into_iter().map(|c| WorkspaceChannelInfo {
+            workspace: c.workspace,
+            platform: c.platform,
+            channel: c.channel,
+        }).collect())
+    }
+
+    /// Answer `channel` with `workspace`'s commands, redeems and config
+    pub async fn assign_channel(
+        client: &GrpcClient,
+        workspace: &str,
+        platform: &str,
+        channel: &str,
+    ) -> Result<(), CommandError> {
+        let mut config_client = client.config.clone();
+        config_client
+            .assign_workspace_channel(AssignWorkspaceChannelRequest {
+                workspace: workspace.to_string(),
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Send `channel` back to the default workspace
+    pub async fn unassign_channel(
+        client: &GrpcClient,
+        platform: &str,
+        channel: &str,
+    ) -> Result<(), CommandError> {
+        let mut config_client = client.config.clone();
+        config_client
+            .unassign_workspace_channel(UnassignWorkspaceChannelRequest {
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+            })
+            .await
+            .map_err(|e| CommandError::GrpcError(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -58,6 +58,10 @@ impl CompletionContext {
     /// Get the current word being typed
     pub fn current_word(&self) -> &str {
         let before = self.text_before_cursor();
+        // After trailing whitespace a new, still-empty word has started
+        if before.ends_with(char::is_whitespace) {
+            return "";
+        }
         before.split_whitespace().last().unwrap_or("")
     }
     
@@ -100,7 +104,8 @@ impl CompletionContext {
     /// Get the platform for this context
     pub fn platform(&self) -> Option<&str> {
         match &self.scope {
-            CompletionScope::TwitchChat { .. } => Some("twitch"),
+            // Chat commands are registered under the IRC platform
+            CompletionScope::TwitchChat { .. } => Some("twitch-irc"),
             CompletionScope::DiscordChat { .. } => Some("discord"),
             CompletionScope::VRChatOSC => Some("vrchat"),
             CompletionScope::OverlayChat { platform, .. } => Some(platform),
@@ -117,4 +122,29 @@ impl CompletionContext {
             _ => None,
         }
     }
+
+    /// The input with the current word swapped for `replacement` and a space,
+    /// and the cursor position just after that space.
+    pub fn replace_current_word(&self, replacement: &str) -> (String, usize) {
+        let cursor = self.text_before_cursor().len();
+        let start = cursor - self.current_word().len();
+        let rest = &self.input[cursor..];
+        let head = format!("{}{} ", &self.input[..start], replacement);
+        let cursor = head.len();
+        (format!("{}{}", head, rest.strip_prefix(' ').unwrap_or(rest)), cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_current_word() {
+        let context = CompletionContext::new(CompletionScope::VRChatOSC, "hi @al there".to_string(), 6);
+        assert_eq!(context.replace_current_word("@alice"), ("hi @alice there".to_string(), 10));
+
+        let context = CompletionContext::new(CompletionScope::VRChatOSC, ":kek".to_string(), 4);
+        assert_eq!(context.replace_current_word("KEKW"), ("KEKW ".to_string(), 5));
+    }
 }
\ No newline at end of file
@@ -75,9 +75,11 @@ impl CompletionEngine {
             }
         }
         
-        // Apply fuzzy matching if enabled
-        if self.config.fuzzy_matching && !prefix.is_empty() {
-            self.apply_fuzzy_matching(&mut all_items, prefix);
+        // Apply fuzzy matching if enabled. Emote names lack the ':' typed
+        // before a shortcode, so match on what follows it.
+        let pattern = if context.is_emote_shortcode() { &prefix[1..] } else { prefix };
+        if self.config.fuzzy_matching && !pattern.is_empty() {
+            self.apply_fuzzy_matching(&mut all_items, pattern);
         }
         
         // Sort by priority and relevance
@@ -113,26 +115,27 @@ impl CompletionEngine {
         let mut scored_items: Vec<(CompletionItem, i64)> = items
             .drain(..)
             .filter_map(|item| {
-                let target = if self.config.case_sensitive {
-                    item.replacement.clone()
+                let (replacement, display) = if self.config.case_sensitive {
+                    (item.replacement.clone(), item.display.clone())
                 } else {
-                    item.replacement.to_lowercase()
+                    (item.replacement.to_lowercase(), item.display.to_lowercase())
                 };
                 
-                self.fuzzy_matcher
-                    .fuzzy_match(&target, &prefix_lower)
-                    .map(|score| (item, score))
+                // Match the display text too, e.g. a channel name whose replacement is an ID
+                let score = self.fuzzy_matcher.fuzzy_match(&replacement, &prefix_lower)
+                    .max(self.fuzzy_matcher.fuzzy_match(&display, &prefix_lower));
+                score.map(|score| (item, score))
             })
             .collect();
         
         // Sort by score (descending)
-        scored_items.sort_by(|a, b| b.1.cmp(&a.1));
+        scored_items.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
         
         // Extract items
         *items = scored_items.into_iter().map(|(item, _)| item).collect();
     }
     
-    fn group_by_category(&self, mut items: Vec<CompletionItem>) -> Vec<CompletionItem> {
+    fn group_by_category(&self, items: Vec<CompletionItem>) -> Vec<CompletionItem> {
         // Group items by category while preserving order within categories
         let mut grouped: Vec<(CompletionCategory, Vec<CompletionItem>)> = Vec::new();
         
@@ -176,6 +179,12 @@ pub struct CompletionEngineBuilder {
     providers: Vec<Box<dyn CompletionProvider>>,
 }
 
+impl Default for CompletionEngineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CompletionEngineBuilder {
     pub fn new() -> Self {
         Self {
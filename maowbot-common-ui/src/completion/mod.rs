@@ -95,6 +95,12 @@ struct CacheEntry {
     ttl: Duration,
 }
 
+impl Default for CompletionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CompletionCache {
     pub fn new() -> Self {
         Self {
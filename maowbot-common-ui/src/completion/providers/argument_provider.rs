@@ -0,0 +1,322 @@
+// Completion provider for TUI command arguments backed by live server data
+use crate::completion::{
+    CompletionCache, CompletionCategory, CompletionContext, CompletionItem, CompletionProvider,
+    CompletionScope,
+};
+use crate::commands::MAX_PAGE_SIZE;
+use crate::GrpcClient;
+use async_trait::async_trait;
+use maowbot_proto::maowbot::common::{PageRequest, Platform};
+use maowbot_proto::prost_types::FieldMask;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Platforms searched for chat command names, matching `command list`
+const COMMAND_PLATFORMS: &[&str] = &["twitch-irc", "twitch", "vrchat", "discord", "twitch-eventsub", "kick"];
+
+/// The kind of server data expected at an argument position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    ChatCommand,
+    /// `multi_word` is true when the adapter joins the remaining args into one name
+    RedeemTitle { multi_word: bool },
+    DiscordChannel,
+    OscParameter,
+}
+
+impl ArgumentKind {
+    /// Key used for the provider's data cache and for invalidation
+    pub fn cache_key(&self) -> &'static str {
+        match self {
+            ArgumentKind::ChatCommand => "arg:commands",
+            ArgumentKind::RedeemTitle { .. } => "arg:redeems",
+            ArgumentKind::DiscordChannel => "arg:discord_channels",
+            ArgumentKind::OscParameter => "arg:osc_parameters",
+        }
+    }
+
+    fn ttl(&self) -> Duration {
+        match self {
+            ArgumentKind::ChatCommand | ArgumentKind::RedeemTitle { .. } => Duration::from_secs(60),
+            ArgumentKind::DiscordChannel => Duration::from_secs(300),
+            // Avatar parameters change whenever the avatar does
+            ArgumentKind::OscParameter => Duration::from_secs(30),
+        }
+    }
+
+    /// Work out which argument kind (if any) the word after `previous` should be.
+    pub fn for_position(previous: &[&str]) -> Option<ArgumentKind> {
+        match previous {
+            ["command", "setcooldown" | "setwarnonce" | "setrespond" | "setplatform"
+                | "enable" | "disable" | "delete"] => Some(ArgumentKind::ChatCommand),
+
+            ["redeem", "info" | "enable" | "disable" | "pause" | "unpause" | "remove"] => {
+                Some(ArgumentKind::RedeemTitle { multi_word: true })
+            }
+            ["redeem", "setcost" | "setprompt" | "setplugin" | "setinput" | "setcommand" | "offline"] => {
+                Some(ArgumentKind::RedeemTitle { multi_word: false })
+            }
+            ["redeem", "setcommand", _] => Some(ArgumentKind::ChatCommand),
+
+            ["discord", "send"] => Some(ArgumentKind::DiscordChannel),
+            ["discord", "event", "add" | "remove", _] => Some(ArgumentKind::DiscordChannel),
+
+            ["osc", "toggle", "test"] => Some(ArgumentKind::OscParameter),
+            ["drip", "fit" | "props", "add" | "del", _] => Some(ArgumentKind::OscParameter),
+
+            _ => None,
+        }
+    }
+
+    /// Cache keys made stale by running `line`, so the next Tab sees the change.
+    pub fn invalidated_by(line: &str) -> Vec<&'static str> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["command", "create" | "delete" | "setplatform", ..] => vec![ArgumentKind::ChatCommand.cache_key()],
+            ["redeem", "add" | "remove" | "sync", ..] => {
+                vec![ArgumentKind::RedeemTitle { multi_word: true }.cache_key()]
+            }
+            ["connection", "start" | "stop", ..] => vec![ArgumentKind::DiscordChannel.cache_key()],
+            ["osc", "start" | "restart", ..] => vec![ArgumentKind::OscParameter.cache_key()],
+            _ => vec![],
+        }
+    }
+}
+
+pub struct ArgumentCompletionProvider {
+    client: Arc<GrpcClient>,
+    cache: Arc<CompletionCache>,
+}
+
+impl ArgumentCompletionProvider {
+    pub fn new(client: Arc<GrpcClient>) -> Self {
+        Self {
+            client,
+            cache: Arc::new(CompletionCache::new()),
+        }
+    }
+
+    /// Shared handle to the fetched data, for invalidation after mutating commands
+    pub fn cache(&self) -> Arc<CompletionCache> {
+        self.cache.clone()
+    }
+
+    async fn fetch(&self, kind: ArgumentKind) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        match kind {
+            ArgumentKind::ChatCommand => self.fetch_chat_commands().await,
+            ArgumentKind::RedeemTitle { .. } => self.fetch_redeems().await,
+            ArgumentKind::DiscordChannel => self.fetch_discord_channels().await,
+            ArgumentKind::OscParameter => self.fetch_osc_parameters().await,
+        }
+    }
+
+    async fn fetch_chat_commands(&self) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        use maowbot_proto::maowbot::services::ListCommandsRequest;
+
+        let mut items: Vec<CompletionItem> = Vec::new();
+        for platform in COMMAND_PLATFORMS {
+            let request = ListCommandsRequest {
+                platform: platform.to_string(),
+                active_only: false,
+                name_prefix: String::new(),
+                page: Some(PageRequest { page_size: MAX_PAGE_SIZE, page_token: String::new() }),
+                order_by: String::new(),
+                descending: false,
+                read_mask: Some(FieldMask { paths: vec!["command".to_string()] }),
+            };
+            let response = self.client.command.clone().list_commands(request).await?;
+
+            for cmd in response.into_inner().commands.into_iter().filter_map(|info| info.command) {
+                if let Some(existing) = items.iter_mut().find(|i| i.replacement == cmd.name) {
+                    if let Some(desc) = existing.description.as_mut() {
+                        desc.push_str(&format!(", {}", cmd.platform));
+                    }
+                    continue;
+                }
+                items.push(CompletionItem {
+                    replacement: cmd.name.clone(),
+                    display: cmd.name,
+                    description: Some(cmd.platform),
+                    category: CompletionCategory::Argument,
+                    icon: Some("!".to_string()),
+                    priority: if cmd.is_active { 80 } else { 70 },
+                    metadata: Default::default(),
+                });
+            }
+        }
+        Ok(items)
+    }
+
+    async fn fetch_redeems(&self) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        use maowbot_proto::maowbot::services::ListRedeemsRequest;
+
+        let request = ListRedeemsRequest {
+            platform: String::new(),
+            active_only: false,
+            dynamic_only: false,
+            page: Some(PageRequest { page_size: MAX_PAGE_SIZE, page_token: String::new() }),
+            order_by: String::new(),
+            descending: false,
+            read_mask: Some(FieldMask { paths: vec!["redeem".to_string()] }),
+            name_prefix: String::new(),
+        };
+        let response = self.client.redeem.clone().list_redeems(request).await?;
+
+        Ok(response.into_inner().redeems
+            .into_iter()
+            .filter_map(|info| info.redeem)
+            .map(|rd| CompletionItem {
+                replacement: rd.reward_name.clone(),
+                display: rd.reward_name,
+                description: Some(format!("{} pts{}", rd.cost, if rd.is_active { "" } else { ", inactive" })),
+                category: CompletionCategory::Argument,
+                icon: Some("★".to_string()),
+                priority: if rd.is_active { 80 } else { 70 },
+                metadata: [("redeem_id".to_string(), rd.redeem_id)].into_iter().collect(),
+            })
+            .collect())
+    }
+
+    async fn fetch_discord_channels(&self) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        use maowbot_proto::maowbot::services::{
+            ChannelType, ListActiveRuntimesRequest, ListChannelsRequest, ListGuildsRequest,
+        };
+
+        let runtimes = self.client.platform.clone()
+            .list_active_runtimes(ListActiveRuntimesRequest { platforms: vec![Platform::Discord as i32] })
+            .await?
+            .into_inner()
+            .runtimes;
+        let Some(runtime) = runtimes.first() else {
+            return Ok(vec![]);
+        };
+        let account_name = runtime.account_name.clone();
+
+        let guilds = self.client.discord.clone()
+            .list_guilds(ListGuildsRequest { account_name: account_name.clone() })
+            .await?
+            .into_inner()
+            .guilds;
+
+        let mut items = Vec::new();
+        for guild in guilds {
+            let request = ListChannelsRequest {
+                account_name: account_name.clone(),
+                guild_id: guild.guild_id.clone(),
+                channel_types: vec![ChannelType::Text as i32],
+            };
+            let channels = match self.client.discord.clone().list_channels(request).await {
+                Ok(resp) => resp.into_inner().channels,
+                Err(e) => {
+                    tracing::debug!("Skipping channels for guild {}: {}", guild.guild_id, e);
+                    continue;
+                }
+            };
+            for channel in channels {
+                items.push(CompletionItem {
+                    replacement: channel.channel_id.clone(),
+                    display: format!("#{}", channel.name),
+                    description: Some(guild.name.clone()),
+                    category: CompletionCategory::Argument,
+                    icon: Some("#".to_string()),
+                    priority: 75,
+                    metadata: [("guild_id".to_string(), guild.guild_id.clone())].into_iter().collect(),
+                });
+            }
+        }
+        Ok(items)
+    }
+
+    async fn fetch_osc_parameters(&self) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        use maowbot_proto::maowbot::services::GetOscAvatarParametersRequest;
+
+        let response = self.client.osc.clone()
+            .get_avatar_parameters(GetOscAvatarParametersRequest { include_values: false })
+            .await?;
+
+        Ok(response.into_inner().parameters
+            .into_iter()
+            .map(|param| CompletionItem {
+                replacement: param.name.clone(),
+                display: param.name,
+                description: None,
+                category: CompletionCategory::Argument,
+                icon: Some("⚙".to_string()),
+                priority: 75,
+                metadata: Default::default(),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for ArgumentCompletionProvider {
+    fn name(&self) -> &str {
+        "arguments"
+    }
+
+    fn is_applicable(&self, context: &CompletionContext) -> bool {
+        matches!(&context.scope, CompletionScope::TuiCommand | CompletionScope::GuiCommand)
+            && ArgumentKind::for_position(&context.previous_words()).is_some()
+    }
+
+    async fn provide_completions(
+        &self,
+        context: &CompletionContext,
+        prefix: &str,
+    ) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(kind) = ArgumentKind::for_position(&context.previous_words()) else {
+            return Ok(vec![]);
+        };
+
+        let items = match self.cache.get(kind.cache_key()) {
+            Some(items) => items,
+            None => {
+                let items = self.fetch(kind).await?;
+                self.cache.set(kind.cache_key().to_string(), items.clone(), kind.ttl());
+                items
+            }
+        };
+
+        let prefix_lower = prefix.to_lowercase();
+        Ok(items
+            .into_iter()
+            .filter(|item| !matches!(kind, ArgumentKind::RedeemTitle { multi_word: false })
+                || !item.replacement.contains(char::is_whitespace))
+            .filter(|item| {
+                item.replacement.to_lowercase().starts_with(&prefix_lower)
+                    || item.display.to_lowercase().trim_start_matches('#').starts_with(&prefix_lower)
+            })
+            .collect())
+    }
+
+    fn cache_duration(&self) -> Duration {
+        // Results depend on the argument position, which the engine's cache key
+        // does not capture; the provider caches fetched data per kind instead.
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_argument_positions() {
+        assert_eq!(ArgumentKind::for_position(&["command", "enable"]), Some(ArgumentKind::ChatCommand));
+        assert_eq!(
+            ArgumentKind::for_position(&["redeem", "setcost"]),
+            Some(ArgumentKind::RedeemTitle { multi_word: false })
+        );
+        assert_eq!(ArgumentKind::for_position(&["redeem", "setcommand", "hydrate"]), Some(ArgumentKind::ChatCommand));
+        assert_eq!(ArgumentKind::for_position(&["discord", "event", "add", "stream.online"]), Some(ArgumentKind::DiscordChannel));
+        assert_eq!(ArgumentKind::for_position(&["command"]), None);
+        assert_eq!(ArgumentKind::for_position(&["command", "enable", "foo"]), None);
+    }
+
+    #[test]
+    fn test_invalidation() {
+        assert_eq!(ArgumentKind::invalidated_by("command create hello twitch-irc"), vec!["arg:commands"]);
+        assert!(ArgumentKind::invalidated_by("command list").is_empty());
+    }
+}
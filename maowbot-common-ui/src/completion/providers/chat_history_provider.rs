@@ -0,0 +1,173 @@
+// Completion provider for chatters and emotes seen in the chat a client is showing
+use crate::chat::ChatEvent;
+use crate::completion::{CompletionProvider, CompletionItem, CompletionCategory, CompletionContext, CompletionScope};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+const MAX_CHATTERS: usize = 200;
+const MAX_EMOTES: usize = 500;
+
+/// Names and emotes from received chat messages, most recently seen first.
+#[derive(Default)]
+pub struct ChatHistory {
+    chatters: VecDeque<String>,
+    emotes: VecDeque<String>,
+}
+
+impl ChatHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Notes the message's author and any Twitch emotes it contains.
+    pub fn record(&mut self, event: &ChatEvent) {
+        if !event.author.is_empty() {
+            touch(&mut self.chatters, &event.author, MAX_CHATTERS);
+        }
+
+        // Emote ranges are inclusive code point indices into the body
+        let chars: Vec<char> = event.body.chars().collect();
+        for range in &event.emotes {
+            let (start, end) = (range.start as usize, range.end as usize);
+            if start <= end && end < chars.len() {
+                let name: String = chars[start..=end].iter().collect();
+                touch(&mut self.emotes, &name, MAX_EMOTES);
+            }
+        }
+    }
+
+    pub fn chatters(&self) -> impl Iterator<Item = &str> {
+        self.chatters.iter().map(String::as_str)
+    }
+
+    pub fn emotes(&self) -> impl Iterator<Item = &str> {
+        self.emotes.iter().map(String::as_str)
+    }
+}
+
+/// Whether the letters of `search` appear in `name` in order, so the engine's
+/// fuzzy matching has loose matches to rank; `search` is lowercase.
+fn loosely_matches(name: &str, search: &str) -> bool {
+    let mut letters = name.chars().flat_map(char::to_lowercase);
+    search.chars().all(|c| letters.any(|l| l == c))
+}
+
+/// Prefix matches first, then the most recently seen.
+fn rank(name: &str, search: &str, recency: usize) -> i32 {
+    let boost = if name.to_lowercase().starts_with(search) { 1000 } else { 0 };
+    boost - recency as i32
+}
+
+/// Moves `name` to the front of `list`, dropping the oldest entry past `cap`.
+fn touch(list: &mut VecDeque<String>, name: &str, cap: usize) {
+    if let Some(pos) = list.iter().position(|n| n.eq_ignore_ascii_case(name)) {
+        list.remove(pos);
+    }
+    list.push_front(name.to_string());
+    list.truncate(cap);
+}
+
+/// Completes `@` mentions from recent chatters and `:` shortcodes from recently
+/// used emotes. Fed by the client as messages arrive, so it needs no server calls.
+pub struct ChatHistoryCompletionProvider {
+    history: Arc<RwLock<ChatHistory>>,
+}
+
+impl ChatHistoryCompletionProvider {
+    pub fn new(history: Arc<RwLock<ChatHistory>>) -> Self {
+        Self { history }
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for ChatHistoryCompletionProvider {
+    fn name(&self) -> &str {
+        "chat_history"
+    }
+
+    fn is_applicable(&self, context: &CompletionContext) -> bool {
+        matches!(
+            &context.scope,
+            CompletionScope::TwitchChat { .. } |
+            CompletionScope::DiscordChat { .. } |
+            CompletionScope::OverlayChat { .. }
+        ) && (context.is_mention() || context.is_emote_shortcode())
+    }
+
+    async fn provide_completions(
+        &self,
+        context: &CompletionContext,
+        prefix: &str,
+    ) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        let history = self.history.read().unwrap();
+
+        let items = if context.is_mention() {
+            let search = prefix.trim_start_matches('@').to_lowercase();
+            history.chatters()
+                .enumerate()
+                .filter(|(_, name)| loosely_matches(name, &search))
+                .map(|(i, name)| CompletionItem {
+                    replacement: format!("@{}", name),
+                    display: format!("@{}", name),
+                    description: None,
+                    category: CompletionCategory::Username,
+                    icon: Some(CompletionCategory::Username.icon().to_string()),
+                    priority: rank(name, &search, i),
+                    metadata: Default::default(),
+                })
+                .collect()
+        } else {
+            let search = prefix.trim_start_matches(':').to_lowercase();
+            history.emotes()
+                .enumerate()
+                .filter(|(_, name)| loosely_matches(name, &search))
+                .map(|(i, name)| CompletionItem {
+                    replacement: name.to_string(),
+                    display: name.to_string(),
+                    description: Some("recent emote".to_string()),
+                    category: CompletionCategory::TwitchEmote,
+                    icon: Some(CompletionCategory::TwitchEmote.icon().to_string()),
+                    priority: rank(name, &search, i),
+                    metadata: Default::default(),
+                })
+                .collect()
+        };
+
+        Ok(items)
+    }
+
+    fn cache_duration(&self) -> Duration {
+        // History changes with every message
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::ChatEmoteRange;
+
+    #[test]
+    fn test_record_chatters_and_emotes() {
+        let mut history = ChatHistory::new();
+        history.record(&ChatEvent {
+            author: "Alice".to_string(),
+            body: "héllo Kappa PogChamp".to_string(),
+            emotes: vec![
+                ChatEmoteRange { emote_id: "25".to_string(), start: 6, end: 10 },
+                ChatEmoteRange { emote_id: "88".to_string(), start: 12, end: 19 },
+                ChatEmoteRange { emote_id: "bad".to_string(), start: 18, end: 40 },
+            ],
+            ..Default::default()
+        });
+        history.record(&ChatEvent { author: "bob".to_string(), body: "hi".to_string(), ..Default::default() });
+        history.record(&ChatEvent { author: "alice".to_string(), body: "again".to_string(), ..Default::default() });
+
+        assert_eq!(history.chatters().collect::<Vec<_>>(), vec!["alice", "bob"]);
+        assert_eq!(history.emotes().collect::<Vec<_>>(), vec!["PogChamp", "Kappa"]);
+        assert!(loosely_matches("PogChamp", "pchp"));
+        assert!(!loosely_matches("Kappa", "kp2"));
+    }
+}
@@ -1,6 +1,9 @@
 // Completion provider for Twitch/Discord commands
 use crate::completion::{CompletionProvider, CompletionItem, CompletionCategory, CompletionContext, CompletionScope};
+use crate::commands::MAX_PAGE_SIZE;
 use crate::GrpcClient;
+use maowbot_proto::maowbot::common::PageRequest;
+use maowbot_proto::prost_types::FieldMask;
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -46,7 +49,10 @@ impl CompletionProvider for CommandCompletionProvider {
             platform: platform.to_string(),
             active_only: true,
             name_prefix: command_prefix.to_string(),
-            page: None,
+            page: Some(PageRequest { page_size: MAX_PAGE_SIZE, page_token: String::new() }),
+            order_by: String::new(),
+            descending: false,
+            read_mask: Some(FieldMask { paths: vec!["command".to_string()] }),
         };
         
         let response = self.client.command.clone()
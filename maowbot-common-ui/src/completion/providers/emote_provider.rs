@@ -1,9 +1,11 @@
 // Completion provider for emotes (Twitch, 7TV, BTTV, FFZ)
 use crate::completion::{CompletionProvider, CompletionItem, CompletionCategory, CompletionContext, CompletionScope};
 use crate::GrpcClient;
+use crate::commands::emote_sets::EmoteSetCommands;
 use async_trait::async_trait;
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,10 +16,13 @@ pub struct EmoteData {
     pub url: Option<String>,
 }
 
+/// When a channel's emotes were fetched, and the emotes
+type CachedEmotes = (Instant, Vec<EmoteData>);
+
 pub struct EmoteCompletionProvider {
     client: Arc<GrpcClient>,
-    // Cache of emotes per channel
-    cache: Arc<tokio::sync::RwLock<HashMap<String, Vec<EmoteData>>>>,
+    // Cache of emotes per channel, with when they were fetched
+    cache: Arc<tokio::sync::RwLock<HashMap<String, CachedEmotes>>>,
 }
 
 impl EmoteCompletionProvider {
@@ -28,45 +33,25 @@ impl EmoteCompletionProvider {
         }
     }
     
+    /// The channel's 7TV, BTTV and FFZ emotes plus the globals, from the
+    /// bot's emote set cache.
     async fn fetch_channel_emotes(&self, channel: &str) -> Vec<EmoteData> {
-        let mut emotes = Vec::new();
-        
-        // Fetch 7TV emotes
-        if let Ok(seventv_emotes) = self.fetch_7tv_emotes(channel).await {
-            emotes.extend(seventv_emotes);
-        }
-        
-        // Fetch BTTV emotes
-        if let Ok(bttv_emotes) = self.fetch_bttv_emotes(channel).await {
-            emotes.extend(bttv_emotes);
-        }
-        
-        // Fetch FFZ emotes
-        if let Ok(ffz_emotes) = self.fetch_ffz_emotes(channel).await {
-            emotes.extend(ffz_emotes);
-        }
-        
         // TODO: Fetch Twitch emotes from API or cache
-        
-        emotes
-    }
-    
-    async fn fetch_7tv_emotes(&self, channel: &str) -> Result<Vec<EmoteData>, Box<dyn std::error::Error + Send + Sync>> {
-        // This would call the 7TV API
-        // For now, return empty vec
-        Ok(vec![])
-    }
-    
-    async fn fetch_bttv_emotes(&self, channel: &str) -> Result<Vec<EmoteData>, Box<dyn std::error::Error + Send + Sync>> {
-        // This would call the BTTV API
-        // For now, return empty vec
-        Ok(vec![])
-    }
-    
-    async fn fetch_ffz_emotes(&self, channel: &str) -> Result<Vec<EmoteData>, Box<dyn std::error::Error + Send + Sync>> {
-        // This would call the FFZ API
-        // For now, return empty vec
-        Ok(vec![])
+        match EmoteSetCommands::list_channel_emotes(&self.client, channel, true).await {
+            Ok(response) => response.emotes
+                .into_iter()
+                .map(|e| EmoteData {
+                    id: e.emote_id,
+                    name: e.emote_name,
+                    provider: e.provider,
+                    url: Some(e.image_url).filter(|url| !url.is_empty()),
+                })
+                .collect(),
+            Err(e) => {
+                tracing::debug!("No third-party emotes for #{}: {}", channel, e);
+                Vec::new()
+            }
+        }
     }
 }
 
@@ -100,17 +85,19 @@ impl CompletionProvider for EmoteCompletionProvider {
         let cache_key = channel.to_string();
         let cached = {
             let cache = self.cache.read().await;
-            cache.get(&cache_key).cloned()
+            cache.get(&cache_key)
+                .filter(|(fetched_at, _)| fetched_at.elapsed() < self.cache_duration())
+                .map(|(_, emotes)| emotes.clone())
         };
         
         let emotes = if let Some(cached_emotes) = cached {
             cached_emotes
         } else {
-            // Fetch and cache
+            // Fetch and cache; an empty answer may be the bot being unreachable, so ask again next time
             let fetched = self.fetch_channel_emotes(channel).await;
-            {
+            if !fetched.is_empty() {
                 let mut cache = self.cache.write().await;
-                cache.insert(cache_key, fetched.clone());
+                cache.insert(cache_key, (Instant::now(), fetched.clone()));
             }
             fetched
         };
@@ -167,8 +154,8 @@ impl CompletionProvider for EmoteCompletionProvider {
         Ok(items)
     }
     
-    fn cache_duration(&self) -> std::time::Duration {
-        // Cache emotes for 30 minutes
-        std::time::Duration::from_secs(1800)
+    fn cache_duration(&self) -> Duration {
+        // The bot keeps the sets current, so only a short local cache
+        Duration::from_secs(300)
     }
 }
\ No newline at end of file
@@ -3,8 +3,14 @@ pub mod command_provider;
 pub mod emote_provider;
 pub mod user_provider;
 pub mod tui_command_provider;
+pub mod pipeline_provider;
+pub mod argument_provider;
+pub mod chat_history_provider;
 
 pub use command_provider::CommandCompletionProvider;
 pub use emote_provider::EmoteCompletionProvider;
 pub use user_provider::UserCompletionProvider;
-pub use tui_command_provider::TuiCommandCompletionProvider;
\ No newline at end of file
+pub use tui_command_provider::TuiCommandCompletionProvider;
+pub use pipeline_provider::PipelineCompletionProvider;
+pub use argument_provider::{ArgumentCompletionProvider, ArgumentKind};
+pub use chat_history_provider::{ChatHistory, ChatHistoryCompletionProvider};
\ No newline at end of file
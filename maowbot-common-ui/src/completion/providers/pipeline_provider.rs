@@ -0,0 +1,76 @@
+// Completion provider for event pipeline names in TUI pipeline commands
+use crate::completion::{CompletionProvider, CompletionItem, CompletionCategory, CompletionContext, CompletionScope};
+use crate::GrpcClient;
+use async_trait::async_trait;
+use maowbot_proto::maowbot::services::event_pipeline::ListPipelinesRequest;
+use std::sync::Arc;
+
+/// Subcommands whose first argument is a pipeline name or ID
+const PIPELINE_ARG_SUBCOMMANDS: &[&str] = &[
+    "delete", "toggle", "enable", "disable", "show", "test", "history", "errors",
+];
+
+pub struct PipelineCompletionProvider {
+    client: Arc<GrpcClient>,
+}
+
+impl PipelineCompletionProvider {
+    pub fn new(client: Arc<GrpcClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for PipelineCompletionProvider {
+    fn name(&self) -> &str {
+        "pipelines"
+    }
+    
+    fn is_applicable(&self, context: &CompletionContext) -> bool {
+        if !matches!(&context.scope, CompletionScope::TuiCommand) {
+            return false;
+        }
+        
+        let words = context.previous_words();
+        match words.as_slice() {
+            ["pipeline", sub] => PIPELINE_ARG_SUBCOMMANDS.contains(sub),
+            ["pipeline", "filter" | "action", "add" | "list"] => true,
+            _ => false,
+        }
+    }
+    
+    async fn provide_completions(
+        &self,
+        _context: &CompletionContext,
+        prefix: &str,
+    ) -> Result<Vec<CompletionItem>, Box<dyn std::error::Error + Send + Sync>> {
+        let request = ListPipelinesRequest { include_disabled: true };
+        let response = self.client.pipeline.clone().list_pipelines(request).await?;
+        
+        let prefix_lower = prefix.to_lowercase();
+        let items = response.into_inner().pipelines
+            .into_iter()
+            .filter(|p| !p.name.contains(char::is_whitespace))
+            .filter(|p| p.name.to_lowercase().starts_with(&prefix_lower))
+            .map(|p| CompletionItem {
+                replacement: p.name.clone(),
+                display: p.name.clone(),
+                description: Some(format!(
+                    "{}{}",
+                    if p.enabled { "enabled" } else { "disabled" },
+                    if p.description.is_empty() { String::new() } else { format!(" - {}", p.description) }
+                )),
+                category: CompletionCategory::Argument,
+                icon: None,
+                priority: if p.enabled { 80 } else { 70 },
+                metadata: [("pipeline_id".to_string(), p.pipeline_id)].into_iter().collect(),
+            })
+            .collect();
+        
+        Ok(items)
+    }
+    
+    fn cache_duration(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(30)
+    }
+}
@@ -13,6 +13,12 @@ struct CommandInfo {
     nested_subcommands: Option<Vec<(String, Vec<String>)>>,
 }
 
+impl Default for TuiCommandCompletionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TuiCommandCompletionProvider {
     pub fn new() -> Self {
         Self {
@@ -41,6 +47,12 @@ impl TuiCommandCompletionProvider {
                 description: "List all plugins".to_string(),
                 nested_subcommands: None,
             },
+            CommandInfo {
+                name: "alias".to_string(),
+                subcommands: vec!["add", "remove", "show", "list"].into_iter().map(String::from).collect(),
+                description: "Command aliases and macros".to_string(),
+                nested_subcommands: None,
+            },
             CommandInfo {
                 name: "quit".to_string(),
                 subcommands: vec![],
@@ -53,7 +65,7 @@ impl TuiCommandCompletionProvider {
                 name: "user".to_string(),
                 subcommands: vec![
                     "add", "remove", "edit", "info", "search", "list",
-                    "chat", "note", "merge", "roles", "analysis"
+                    "card", "chat", "note", "history", "merge", "roles", "analysis", "analyze"
                 ].into_iter().map(String::from).collect(),
                 description: "User management".to_string(),
                 nested_subcommands: None,
@@ -76,13 +88,13 @@ impl TuiCommandCompletionProvider {
             },
             CommandInfo {
                 name: "account".to_string(),
-                subcommands: vec!["add", "remove", "list", "show", "refresh"].into_iter().map(String::from).collect(),
+                subcommands: vec!["add", "remove", "list", "show", "refresh", "scopes", "reauth"].into_iter().map(String::from).collect(),
                 description: "Account management".to_string(),
                 nested_subcommands: None,
             },
             CommandInfo {
                 name: "connection".to_string(),
-                subcommands: vec!["start", "stop", "status", "autostart", "chat"].into_iter().map(String::from).collect(),
+                subcommands: vec!["start", "stop", "status", "autostart", "chat", "identity"].into_iter().map(String::from).collect(),
                 description: "Connection management".to_string(),
                 nested_subcommands: None,
             },
@@ -91,7 +103,8 @@ impl TuiCommandCompletionProvider {
             CommandInfo {
                 name: "command".to_string(),
                 subcommands: vec![
-                    "list", "setcooldown", "setwarnonce", "setrespond", "enable", "disable"
+                    "list", "stats", "import", "setresponse", "setcooldown", "setusercooldown", "setrolecooldown",
+                    "setbypassmods", "setprivate", "setreply", "setwarnonce", "setrespond", "enable", "disable"
                 ].into_iter().map(String::from).collect(),
                 description: "Command management".to_string(),
                 nested_subcommands: None,
@@ -99,7 +112,7 @@ impl TuiCommandCompletionProvider {
             CommandInfo {
                 name: "redeem".to_string(),
                 subcommands: vec![
-                    "list", "create", "delete", "cost", "enable", "disable"
+                    "list", "create", "delete", "cost", "enable", "disable", "sync", "schedule"
                 ].into_iter().map(String::from).collect(),
                 description: "Redeem management".to_string(),
                 nested_subcommands: None,
@@ -107,18 +120,141 @@ impl TuiCommandCompletionProvider {
             CommandInfo {
                 name: "config".to_string(),
                 subcommands: vec![
-                    "list", "get", "set", "delete", "export", "import"
+                    "list", "get", "set", "set-secret", "delete", "schema", "export", "import", "rotate-key",
+                    "export-all", "import-all"
                 ].into_iter().map(String::from).collect(),
                 description: "Configuration management".to_string(),
+                nested_subcommands: Some(vec![
+                    ("schema".to_string(), vec![
+                        "general", "twitch", "vrchat", "osc", "chat_logging", "chat_cache"
+                    ].into_iter().map(String::from).collect()),
+                ]),
+            },
+            CommandInfo {
+                name: "workspace".to_string(),
+                subcommands: vec![
+                    "list", "create", "use", "delete", "current", "channels", "channel"
+                ].into_iter().map(String::from).collect(),
+                description: "Workspace (broadcaster) selection".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "token".to_string(),
+                subcommands: vec![
+                    "list", "create", "revoke", "audit"
+                ].into_iter().map(String::from).collect(),
+                description: "API tokens and access audit".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "audit".to_string(),
+                subcommands: vec!["list".to_string()],
+                description: "Audit log of state-changing calls".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "chatlog".to_string(),
+                subcommands: vec![
+                    "search", "lastseen", "retention", "activity"
+                ].into_iter().map(String::from).collect(),
+                description: "Chat history search and retention".to_string(),
+                nested_subcommands: Some(vec![
+                    ("retention".to_string(), vec!["list".to_string(), "set".to_string(), "clear".to_string()]),
+                ]),
+            },
+            CommandInfo {
+                name: "export".to_string(),
+                subcommands: vec![
+                    "chat", "events", "commands"
+                ].into_iter().map(String::from).collect(),
+                description: "Export chat, events or command uses to CSV/JSONL".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "giveaway".to_string(),
+                subcommands: vec![
+                    "open", "list", "show", "close", "cancel", "draw", "reroll"
+                ].into_iter().map(String::from).collect(),
+                description: "Giveaways with draws and re-rolls".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "vote".to_string(),
+                subcommands: vec![
+                    "start", "status", "end", "cancel"
+                ].into_iter().map(String::from).collect(),
+                description: "Crowd control votes among OSC effects".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "protect".to_string(),
+                subcommands: vec![
+                    "status", "shield", "lockdown", "lift", "incidents", "incident"
+                ].into_iter().map(String::from).collect(),
+                description: "Raid defense and Shield Mode".to_string(),
+                nested_subcommands: Some(vec![
+                    ("shield".to_string(), vec!["on".to_string(), "off".to_string()]),
+                ]),
+            },
+            CommandInfo {
+                name: "automod".to_string(),
+                subcommands: vec![
+                    "list", "add", "edit", "remove", "enable", "disable", "reset", "test"
+                ].into_iter().map(String::from).collect(),
+                description: "Link and phrase moderation rules".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "responder".to_string(),
+                subcommands: vec![
+                    "list", "add", "edit", "remove", "enable", "disable", "test"
+                ].into_iter().map(String::from).collect(),
+                description: "Regex and keyword chat responders".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "alerting".to_string(),
+                subcommands: vec![
+                    "rules", "add", "set", "remove", "enable", "disable", "recent", "test"
+                ].into_iter().map(String::from).collect(),
+                description: "Ops alert rules and recent alerts".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "emotes".to_string(),
+                subcommands: vec!["top", "sets", "refresh"].into_iter().map(String::from).collect(),
+                description: "Emote usage stats and third-party emote sets".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "session".to_string(),
+                subcommands: vec!["list", "show"].into_iter().map(String::from).collect(),
+                description: "Stream sessions and per-stream stats".to_string(),
+                nested_subcommands: None,
+            },
+            CommandInfo {
+                name: "language".to_string(),
+                subcommands: vec![
+                    "list", "channel", "keys", "set", "unset", "import",
+                ].into_iter().map(String::from).collect(),
+                description: "Bot response languages".to_string(),
                 nested_subcommands: None,
             },
             CommandInfo {
                 name: "pipeline".to_string(),
-                subcommands: vec!["list", "create", "delete", "toggle", "show", "filter", "action", "history", "reload"].into_iter().map(String::from).collect(),
+                subcommands: vec!["list", "create", "import", "delete", "toggle", "enable", "disable", "show", "test", "throttle", "filter", "action", "history", "errors", "reload"].into_iter().map(String::from).collect(),
                 description: "Event pipeline management".to_string(),
                 nested_subcommands: Some(vec![
                     ("filter".to_string(), vec!["add".to_string(), "remove".to_string(), "list".to_string(), "types".to_string()]),
-                    ("action".to_string(), vec!["add".to_string(), "remove".to_string(), "list".to_string(), "types".to_string()]),
+                    ("action".to_string(), vec!["add".to_string(), "condition".to_string(), "remove".to_string(), "list".to_string(), "types".to_string()]),
+                ]),
+            },
+            CommandInfo {
+                name: "simulate".to_string(),
+                subcommands: vec!["list", "eventsub", "discord", "slash", "raid", "follow", "sub", "gift-subs", "hype-progress", "redeem", "cheer", "shoutout"].into_iter().map(String::from).collect(),
+                description: "Publish synthetic EventSub and Discord events".to_string(),
+                nested_subcommands: Some(vec![
+                    ("discord".to_string(), vec!["message".to_string()]),
                 ]),
             },
             
@@ -126,7 +262,7 @@ impl TuiCommandCompletionProvider {
             CommandInfo {
                 name: "twitch".to_string(),
                 subcommands: vec![
-                    "active", "join", "part", "msg", "chat", "default"
+                    "active", "join", "part", "msg", "chat", "default", "marker", "chapters", "eventsub"
                 ].into_iter().map(String::from).collect(),
                 description: "Twitch-specific commands".to_string(),
                 nested_subcommands: None,
@@ -161,7 +297,7 @@ impl TuiCommandCompletionProvider {
             },
             CommandInfo {
                 name: "vrchat".to_string(),
-                subcommands: vec!["world", "avatar", "instance"].into_iter().map(String::from).collect(),
+                subcommands: vec!["world", "avatar", "instance", "account", "here", "session", "login", "group"].into_iter().map(String::from).collect(),
                 description: "VRChat integration".to_string(),
                 nested_subcommands: None,
             },
@@ -223,21 +359,34 @@ impl TuiCommandCompletionProvider {
             },
             CommandInfo {
                 name: "diagnostics".to_string(),
-                subcommands: vec!["health", "status", "metrics", "logs", "test"].into_iter().map(String::from).collect(),
+                subcommands: vec!["health", "run", "status", "ready", "metrics", "logs", "test"].into_iter().map(String::from).collect(),
                 description: "System diagnostics".to_string(),
                 nested_subcommands: None,
             },
+            CommandInfo {
+                name: "watch".to_string(),
+                subcommands: vec!["status", "osc", "connections", "events"].into_iter().map(String::from).collect(),
+                description: "Live status dashboards and the event stream".to_string(),
+                nested_subcommands: None,
+            },
             CommandInfo {
                 name: "diag".to_string(), // Alias
-                subcommands: vec!["health", "status", "metrics", "logs", "test"].into_iter().map(String::from).collect(),
+                subcommands: vec!["health", "run", "status", "ready", "metrics", "logs", "test"].into_iter().map(String::from).collect(),
                 description: "System diagnostics (alias)".to_string(),
                 nested_subcommands: None,
             },
             CommandInfo {
                 name: "system".to_string(),
-                subcommands: vec!["server", "overlay"].into_iter().map(String::from).collect(),
+                subcommands: vec!["server", "overlay", "shutdown", "log", "update", "db", "cert", "service", "install-service", "uninstall-service"].into_iter().map(String::from).collect(),
                 description: "Process management".to_string(),
-                nested_subcommands: None,
+                nested_subcommands: Some(vec![
+                    ("log".to_string(), vec!["levels".to_string(), "set".to_string(), "reset".to_string()]),
+                    ("update".to_string(), vec!["status".to_string(), "check".to_string(), "apply".to_string(), "auto".to_string()]),
+                    ("db".to_string(), vec!["status".to_string(), "snapshot".to_string(), "compact".to_string(), "check".to_string(), "retention".to_string()]),
+                    ("cert".to_string(), vec!["show".to_string(), "regenerate".to_string(), "issue".to_string()]),
+                    ("service".to_string(), vec!["start".to_string(), "stop".to_string(), "status".to_string()]),
+                    ("install-service".to_string(), vec!["--system".to_string(), "--user".to_string(), "--db".to_string()]),
+                ]),
             },
         ]
     }
@@ -14,7 +14,7 @@ impl UserCompletionProvider {
         Self { client }
     }
     
-    async fn get_recent_chatters(&self, channel: &str, limit: usize) -> Vec<(String, Vec<String>)> {
+    async fn get_recent_chatters(&self, _channel: &str, _limit: usize) -> Vec<(String, Vec<String>)> {
         // TODO: This should query the message cache service
         // For now, we'll use a placeholder that would be replaced with actual gRPC call
         
@@ -36,10 +36,11 @@ impl CompletionProvider for UserCompletionProvider {
     fn is_applicable(&self, context: &CompletionContext) -> bool {
         // Complete usernames when:
         // 1. Typing an @mention
-        // 2. In TUI for user commands
+        // 2. In TUI for user command arguments (not the subcommand itself)
         context.is_mention() || 
         (matches!(&context.scope, crate::completion::CompletionScope::TuiCommand) && 
-         context.previous_words().get(0) == Some(&"user"))
+         context.previous_words().first() == Some(&"user") &&
+         context.previous_words().len() >= 2)
     }
     
     async fn provide_completions(
@@ -86,9 +87,11 @@ impl CompletionProvider for UserCompletionProvider {
                     active_only: true,
                     platforms: vec![],
                     roles: vec![],
+                    name_prefix: String::new(),
                 }),
                 order_by: "last_seen".to_string(),
                 descending: true,
+                read_mask: None,
             };
             
             if let Ok(response) = self.client.user.clone().list_users(request).await {
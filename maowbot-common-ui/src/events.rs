@@ -1,4 +1,10 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
 use crate::chat::ChatEvent;
+use crate::process_manager::ProcessType;
+use crate::settings_sync::SharedSettings;
 
 #[derive(Clone)]
 pub enum UIEvent {
@@ -16,10 +22,123 @@ pub enum UIEvent {
 pub enum AppEvent {
     Chat(ChatEvent),
     OverlayStatusChanged(bool),
-    GrpcStatusChanged(bool),
+    /// A managed process exited on its own with an error
+    ProcessCrashed {
+        process: ProcessType,
+        exit: String,
+        /// The crash report, if one could be written
+        report: Option<PathBuf>,
+        restarting: bool,
+    },
+    GrpcStatusChanged(ConnectionStatus),
+    /// Latest BPM from the bot's heart rate source; None once it disconnects
+    HeartRate(Option<u16>),
+    /// A crowd control vote opened, got new votes or ended
+    CrowdVote(CrowdVoteTally),
+    /// An operational alert the bot routed to the desktop
+    OpsAlert {
+        severity: String,
+        title: String,
+        message: String,
+    },
+    /// Settings another client saved on the server
+    SettingsChanged(SharedSettings),
     Shutdown,
 }
 
+/// One option of a crowd control vote with its votes so far.
+#[derive(Debug, Clone)]
+pub struct CrowdVoteOptionTally {
+    pub label: String,
+    pub votes: u32,
+    /// Sum of the voters' weights, which decides the winner
+    pub weight: u64,
+}
+
+/// A crowd control vote as the bot's `crowd_vote` event describes it.
+#[derive(Debug, Clone)]
+pub struct CrowdVoteTally {
+    pub title: String,
+    pub options: Vec<CrowdVoteOptionTally>,
+    /// open, decided, no_votes or cancelled
+    pub status: String,
+    /// Index of the winning option
+    pub winner: Option<usize>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl CrowdVoteTally {
+    pub fn from_json(json: &str) -> Option<CrowdVoteTally> {
+        let payload: serde_json::Value = serde_json::from_str(json).ok()?;
+        let vote = payload.get("vote")?;
+        let text = |v: &serde_json::Value, key: &str| v.get(key).and_then(|s| s.as_str()).unwrap_or_default().to_string();
+        let options: Vec<CrowdVoteOptionTally> = vote.get("options")?.as_array()?.iter()
+            .map(|o| CrowdVoteOptionTally {
+                label: text(o, "label"),
+                votes: o.get("votes").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+                weight: o.get("weight").and_then(|v| v.as_u64()).unwrap_or(0),
+            })
+            .collect();
+        let winner = vote.get("winner").and_then(|w| w.as_str())
+            .and_then(|w| options.iter().position(|o| o.label == w));
+        Some(CrowdVoteTally {
+            title: text(vote, "title"),
+            status: text(vote, "status"),
+            ends_at: vote.get("ends_at").and_then(|t| t.as_str())
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc)),
+            options,
+            winner,
+        })
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == "open"
+    }
+
+    /// Whole seconds until voting closes; 0 once it has.
+    pub fn seconds_left(&self) -> u32 {
+        match self.ends_at {
+            Some(ends_at) if self.is_open() => (ends_at - Utc::now()).num_seconds().max(0) as u32,
+            _ => 0,
+        }
+    }
+}
+
+/// Where the connection to the bot stands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    /// Trying to connect; `attempt` counts up until one succeeds
+    Connecting { attempt: u32 },
+    Connected,
+    /// Lost or never made; the next attempt starts after `retry_in`.
+    /// `queued` chat messages go out once it succeeds.
+    Disconnected { reason: String, retry_in: Duration, queued: usize },
+}
+
+impl ConnectionStatus {
+    pub fn is_connected(&self) -> bool {
+        matches!(self, ConnectionStatus::Connected)
+    }
+}
+
+impl fmt::Display for ConnectionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionStatus::Connecting { attempt: 1 } => write!(f, "Connecting..."),
+            ConnectionStatus::Connecting { attempt } => write!(f, "Reconnecting (attempt {})...", attempt),
+            ConnectionStatus::Connected => write!(f, "Connected"),
+            ConnectionStatus::Disconnected { reason, retry_in, queued } => {
+                write!(f, "Offline: {} - retrying in {}s", reason, retry_in.as_secs())?;
+                if *queued > 0 {
+                    write!(f, " ({} message(s) waiting)", queued)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 pub enum ChatCommand {
     SendMessage(String),
 }
\ No newline at end of file
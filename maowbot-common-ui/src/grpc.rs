@@ -1,7 +1,11 @@
 use crate::{AppEvent, ChatEvent};
+use crate::events::{ChatCommand, ConnectionStatus, CrowdVoteTally};
 use anyhow::Result;
 use crossbeam_channel::{Receiver, Sender};
-use tokio::sync::mpsc::unbounded_channel;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::time::{sleep_until, Instant};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tonic::transport::{Certificate, ClientTlsConfig, Endpoint};
 
@@ -9,9 +13,14 @@ use maowbot_proto::plugs::{
     plugin_service_client::PluginServiceClient,
     plugin_stream_request::Payload as ReqPayload,
     plugin_stream_response::Payload as RespPayload,
-    Hello, PluginCapability, PluginStreamRequest, SendChat,
+    Hello, PluginCapability, PluginStreamRequest, PluginStreamResponse, SendChat,
 };
-use crate::events::ChatCommand;
+
+/// Wait before the first reconnect; doubles after every failed attempt.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Chat messages held while offline; the oldest go first past this.
+const OFFLINE_QUEUE_LIMIT: usize = 50;
 
 pub struct SharedGrpcClient;
 
@@ -27,47 +36,94 @@ impl SharedGrpcClient {
         let ca_path = std::env::var("MAOWBOT_GRPC_CA")
             .unwrap_or_else(|_| "certs/server.crt".into());
 
+        // Waiting on the crossbeam receiver blocks, so a plain thread moves
+        // commands over to a channel the connection task can select on
+        let (cmd_tx, mut cmd_rx) = unbounded_channel();
+        std::thread::spawn(move || {
+            while let Ok(cmd) = command_rx.recv() {
+                if cmd_tx.send(cmd).is_err() {
+                    break;
+                }
+            }
+        });
+
         tokio::spawn(async move {
+            let mut queue = VecDeque::new();
+            let mut backoff = MIN_BACKOFF;
+            let mut attempt = 1;
             loop {
-                match Self::connect_and_run(
+                let _ = event_tx.send(AppEvent::GrpcStatusChanged(ConnectionStatus::Connecting { attempt }));
+
+                let reason = match Self::connect_and_run(
                     &url,
                     &token,
                     &ca_path,
                     &plugin_name,
-                    event_tx.clone(),
-                    command_rx.clone(),
+                    &event_tx,
+                    &mut cmd_rx,
+                    &mut queue,
                 ).await {
-                    Ok(_) => {
-                        tracing::info!("gRPC connection closed normally");
+                    Ok(reason) => {
+                        tracing::info!("gRPC connection closed: {}", reason);
+                        backoff = MIN_BACKOFF;
+                        attempt = 1;
+                        reason
                     }
                     Err(e) => {
-                        tracing::error!("gRPC connection error: {}", e);
-                        let _ = event_tx.send(AppEvent::GrpcStatusChanged(false));
+                        tracing::error!("gRPC connection error: {:#}", e);
+                        attempt += 1;
+                        format!("{:#}", e)
                     }
-                }
+                };
+                let _ = event_tx.send(AppEvent::GrpcStatusChanged(ConnectionStatus::Disconnected {
+                    reason: reason.clone(),
+                    retry_in: backoff,
+                    queued: queue.len(),
+                }));
 
-                // Wait before reconnecting
-                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                // Keep taking chat while waiting so it goes out after reconnecting
+                let retry_at = Instant::now() + backoff;
+                loop {
+                    tokio::select! {
+                        _ = sleep_until(retry_at) => break,
+                        Some(cmd) = cmd_rx.recv() => {
+                            queue_command(&mut queue, cmd);
+                            let _ = event_tx.send(AppEvent::GrpcStatusChanged(ConnectionStatus::Disconnected {
+                                reason: reason.clone(),
+                                retry_in: retry_at.saturating_duration_since(Instant::now()),
+                                queued: queue.len(),
+                            }));
+                        }
+                    }
+                }
+                backoff = (backoff * 2).min(MAX_BACKOFF);
             }
         });
     }
 
+    /// Runs one session. Errors mean no session was established; once one
+    /// is, returns why it ended.
     async fn connect_and_run(
         url: &str,
         token: &str,
         ca_path: &str,
         plugin_name: &str,
-        event_tx: Sender<AppEvent>,
-        command_rx: Receiver<ChatCommand>,
-    ) -> Result<()> {
+        event_tx: &Sender<AppEvent>,
+        cmd_rx: &mut UnboundedReceiver<ChatCommand>,
+        queue: &mut VecDeque<ChatCommand>,
+    ) -> Result<String> {
         let mut endpoint = Endpoint::new(url.to_string())?;
 
         if url.starts_with("https://") {
-            let ca = tokio::fs::read(ca_path).await?;
+            let ca = tokio::fs::read(ca_path).await
+                .map_err(|e| anyhow::anyhow!("reading CA certificate {}: {}", ca_path, e))?;
             let ca_cert = Certificate::from_pem(ca);
-            let tls = ClientTlsConfig::new()
+            let mut tls = ClientTlsConfig::new()
                 .ca_certificate(ca_cert)
                 .domain_name("localhost");
+            if let Some(identity) = crate::grpc_client::client_identity() {
+                tls = tls.identity(identity);
+            }
             endpoint = endpoint.tls_config(tls)?;
         }
 
@@ -99,36 +155,107 @@ impl SharedGrpcClient {
             )),
         })?;
 
-        let _ = event_tx.send(AppEvent::GrpcStatusChanged(true));
+        let _ = event_tx.send(AppEvent::GrpcStatusChanged(ConnectionStatus::Connected));
 
-        // Spawn command handler
-        let tx_out_clone = tx_out.clone();
-        tokio::spawn(async move {
-            while let Ok(cmd) = command_rx.recv() {
-                match cmd {
-                    ChatCommand::SendMessage(text) => {
-                        let _ = tx_out_clone.send(PluginStreamRequest {
-                            payload: Some(ReqPayload::SendChat(SendChat {
-                                channel: "twitch".into(),
-                                text,
-                            })),
-                        });
+        // What was typed while offline goes out first
+        if !queue.is_empty() {
+            tracing::info!("Sending {} chat message(s) queued while offline", queue.len());
+        }
+        while let Some(cmd) = queue.pop_front() {
+            tx_out.send(chat_request(cmd))?;
+        }
+
+        loop {
+            tokio::select! {
+                message = inbound.message() => match message {
+                    Ok(Some(msg)) => handle_message(msg, event_tx),
+                    Ok(None) => return Ok("server closed the connection".into()),
+                    Err(status) => return Ok(format!("connection lost: {}", status.message())),
+                },
+                Some(cmd) = cmd_rx.recv() => {
+                    if let Err(e) = tx_out.send(chat_request(cmd)) {
+                        // The session is gone; keep the message for the next one
+                        if let Some(ReqPayload::SendChat(chat)) = e.0.payload {
+                            queue.push_front(ChatCommand::SendMessage(chat.text));
+                        }
+                        return Ok("connection lost while sending".into());
                     }
                 }
             }
-        });
+        }
+    }
+}
 
-        // Message pump
-        while let Ok(Some(msg)) = inbound.message().await {
-            if let Some(RespPayload::ChatMessage(cm)) = msg.payload {
-                let _ = event_tx.send(AppEvent::Chat(ChatEvent {
-                    channel: cm.channel,
-                    author: cm.user,
-                    body: cm.text,
-                }));
+fn chat_request(cmd: ChatCommand) -> PluginStreamRequest {
+    match cmd {
+        ChatCommand::SendMessage(text) => PluginStreamRequest {
+            payload: Some(ReqPayload::SendChat(SendChat {
+                channel: "twitch".into(),
+                text,
+            })),
+        },
+    }
+}
+
+/// Adds `cmd` to the offline queue, dropping the oldest message when full.
+fn queue_command(queue: &mut VecDeque<ChatCommand>, cmd: ChatCommand) {
+    if queue.len() >= OFFLINE_QUEUE_LIMIT {
+        queue.pop_front();
+        tracing::warn!("Offline chat queue full; dropped the oldest message");
+    }
+    queue.push_back(cmd);
+}
+
+fn handle_message(msg: PluginStreamResponse, event_tx: &Sender<AppEvent>) {
+    match msg.payload {
+        Some(RespPayload::ChatMessage(cm)) => {
+            let author = if cm.display_name.is_empty() { cm.user } else { cm.display_name };
+            let _ = event_tx.send(AppEvent::Chat(ChatEvent {
+                channel: cm.channel,
+                author,
+                body: cm.text,
+                badges: cm.badges,
+                color: Some(cm.color).filter(|c| !c.is_empty()),
+                reply_to: cm.reply_parent,
+                emotes: cm.emotes,
+            }));
+        }
+        Some(RespPayload::GameEvent(ge)) if ge.name == "heart_rate" => {
+            let bpm = serde_json::from_str::<serde_json::Value>(&ge.json)
+                .ok()
+                .and_then(|v| v.get("bpm").and_then(|b| b.as_u64()))
+                .and_then(|b| u16::try_from(b).ok());
+            let _ = event_tx.send(AppEvent::HeartRate(bpm));
+        }
+        Some(RespPayload::GameEvent(ge)) if ge.name == "crowd_vote" => {
+            if let Some(tally) = CrowdVoteTally::from_json(&ge.json) {
+                let _ = event_tx.send(AppEvent::CrowdVote(tally));
             }
         }
+        Some(RespPayload::GameEvent(ge)) if ge.name == "ops_alert" => {
+            let Ok(alert) = serde_json::from_str::<serde_json::Value>(&ge.json) else { return };
+            let field = |key: &str| alert.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let _ = event_tx.send(AppEvent::OpsAlert {
+                severity: field("severity"),
+                title: field("title"),
+                message: field("message"),
+            });
+        }
+        _ => {}
+    }
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_queue_drops_oldest_when_full() {
+        let mut queue = VecDeque::new();
+        for i in 0..=OFFLINE_QUEUE_LIMIT {
+            queue_command(&mut queue, ChatCommand::SendMessage(i.to_string()));
+        }
+        assert_eq!(queue.len(), OFFLINE_QUEUE_LIMIT);
+        assert!(matches!(queue.front(), Some(ChatCommand::SendMessage(text)) if text == "1"));
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,7 @@
-use tonic::transport::{Channel, Endpoint};
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::{interceptor::InterceptedService, Interceptor};
+use tonic::transport::{Channel, Endpoint, Identity};
+use tonic::{Request, Status};
 use maowbot_proto::maowbot::services::{
     user_service_client::UserServiceClient,
     credential_service_client::CredentialServiceClient,
@@ -15,26 +18,114 @@ use maowbot_proto::maowbot::services::{
     autostart_service_client::AutostartServiceClient,
     obs_service_client::ObsServiceClient,
     event_pipeline::event_pipeline_service_client::EventPipelineServiceClient,
+    chat_archive_service_client::ChatArchiveServiceClient,
+    giveaway_service_client::GiveawayServiceClient,
+    crowd_vote_service_client::CrowdVoteServiceClient,
+    protection_service_client::ProtectionServiceClient,
+    moderation_rules_service_client::ModerationRulesServiceClient,
+    emote_stats_service_client::EmoteStatsServiceClient,
+    emote_set_service_client::EmoteSetServiceClient,
+    localization_service_client::LocalizationServiceClient,
+    event_stream_service_client::EventStreamServiceClient,
+    ui_settings_service_client::UiSettingsServiceClient,
+    responder_service_client::ResponderServiceClient,
+    alerting_service_client::AlertingServiceClient,
+    health_service_client::HealthServiceClient,
+    stream_session_service_client::StreamSessionServiceClient,
+    export_service_client::ExportServiceClient,
 };
+use maowbot_proto::{AUTHORIZATION_METADATA_KEY, WORKSPACE_METADATA_KEY};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+/// Environment variable holding the API token sent to the server.
+pub const API_TOKEN_ENV: &str = "MAOWBOT_API_TOKEN";
+
+/// Environment variables overriding where the client certificate for mutual TLS is read from.
+pub const CLIENT_CERT_ENV: &str = "MAOWBOT_GRPC_CLIENT_CERT";
+pub const CLIENT_KEY_ENV: &str = "MAOWBOT_GRPC_CLIENT_KEY";
+
+/// The client certificate presented when the server requires mutual TLS:
+/// `MAOWBOT_GRPC_CLIENT_CERT`/`_KEY`, or else `certs/client.crt`/`client.key`,
+/// which the server writes for local clients when it creates its CA.
+pub fn client_identity() -> Option<Identity> {
+    let cert_path = std::env::var(CLIENT_CERT_ENV).unwrap_or_else(|_| "certs/client.crt".into());
+    let key_path = std::env::var(CLIENT_KEY_ENV).unwrap_or_else(|_| "certs/client.key".into());
+    let cert = std::fs::read(cert_path).ok()?;
+    let key = std::fs::read(key_path).ok()?;
+    Some(Identity::from_pem(cert, key))
+}
+
+/// Adds the API token and the selected workspace (if any) to every outgoing request.
+/// Clones share both, so changing them affects all service clients.
+#[derive(Clone, Default)]
+pub struct SessionInterceptor {
+    workspace: Arc<RwLock<Option<MetadataValue<Ascii>>>>,
+    authorization: Arc<RwLock<Option<MetadataValue<Ascii>>>>,
+}
+
+impl Interceptor for SessionInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(value) = self.authorization.read().unwrap().clone() {
+            request.metadata_mut().insert(AUTHORIZATION_METADATA_KEY, value);
+        }
+        if let Some(value) = self.workspace.read().unwrap().clone() {
+            request.metadata_mut().insert(WORKSPACE_METADATA_KEY, value);
+        }
+        Ok(request)
+    }
+}
+
+/// The token from `MAOWBOT_API_TOKEN`, or else the local admin token the server
+/// writes to `<config dir>/maowbot/admin.token` on first start.
+pub fn default_api_token() -> Option<String> {
+    if let Ok(token) = std::env::var(API_TOKEN_ENV) {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+    let path = dirs::config_dir()?.join("maowbot").join("admin.token");
+    std::fs::read_to_string(path).ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+pub type ScopedChannel = InterceptedService<Channel, SessionInterceptor>;
+
 #[derive(Clone)]
 pub struct GrpcClient {
-    pub user: UserServiceClient<Channel>,
-    pub credential: CredentialServiceClient<Channel>,
-    pub platform: PlatformServiceClient<Channel>,
-    pub command: CommandServiceClient<Channel>,
-    pub redeem: RedeemServiceClient<Channel>,
-    pub config: ConfigServiceClient<Channel>,
-    pub ai: AiServiceClient<Channel>,
-    pub plugin: PluginServiceClient<Channel>,
-    pub osc: OscServiceClient<Channel>,
-    pub twitch: TwitchServiceClient<Channel>,
-    pub discord: DiscordServiceClient<Channel>,
-    pub vrchat: VrChatServiceClient<Channel>,
-    pub autostart: AutostartServiceClient<Channel>,
-    pub obs: ObsServiceClient<Channel>,
-    pub pipeline: EventPipelineServiceClient<Channel>,
+    pub user: UserServiceClient<ScopedChannel>,
+    pub credential: CredentialServiceClient<ScopedChannel>,
+    pub platform: PlatformServiceClient<ScopedChannel>,
+    pub command: CommandServiceClient<ScopedChannel>,
+    pub redeem: RedeemServiceClient<ScopedChannel>,
+    pub config: ConfigServiceClient<ScopedChannel>,
+    pub ai: AiServiceClient<ScopedChannel>,
+    pub plugin: PluginServiceClient<ScopedChannel>,
+    pub osc: OscServiceClient<ScopedChannel>,
+    pub twitch: TwitchServiceClient<ScopedChannel>,
+    pub discord: DiscordServiceClient<ScopedChannel>,
+    pub vrchat: VrChatServiceClient<ScopedChannel>,
+    pub autostart: AutostartServiceClient<ScopedChannel>,
+    pub obs: ObsServiceClient<ScopedChannel>,
+    pub pipeline: EventPipelineServiceClient<ScopedChannel>,
+    pub chat_archive: ChatArchiveServiceClient<ScopedChannel>,
+    pub giveaway: GiveawayServiceClient<ScopedChannel>,
+    pub crowd_vote: CrowdVoteServiceClient<ScopedChannel>,
+    pub protection: ProtectionServiceClient<ScopedChannel>,
+    pub moderation_rules: ModerationRulesServiceClient<ScopedChannel>,
+    pub emote_stats: EmoteStatsServiceClient<ScopedChannel>,
+    pub emote_sets: EmoteSetServiceClient<ScopedChannel>,
+    pub localization: LocalizationServiceClient<ScopedChannel>,
+    pub events: EventStreamServiceClient<ScopedChannel>,
+    pub ui_settings: UiSettingsServiceClient<ScopedChannel>,
+    pub responders: ResponderServiceClient<ScopedChannel>,
+    pub alerting: AlertingServiceClient<ScopedChannel>,
+    pub health: HealthServiceClient<ScopedChannel>,
+    pub sessions: StreamSessionServiceClient<ScopedChannel>,
+    pub export: ExportServiceClient<ScopedChannel>,
+    session: SessionInterceptor,
 }
 
 impl GrpcClient {
@@ -44,8 +135,9 @@ impl GrpcClient {
             // For HTTPS with self-signed certs, configure TLS
             let mut tls = tonic::transport::ClientTlsConfig::new();
             
-            // Try to load the server's certificate if it exists
-            if let Ok(cert_pem) = std::fs::read("certs/server.crt") {
+            // Trust the server's certificate chain (or the CA copied from it) if we have it
+            let ca_path = std::env::var("MAOWBOT_GRPC_CA").unwrap_or_else(|_| "certs/server.crt".into());
+            if let Ok(cert_pem) = std::fs::read(ca_path) {
                 let ca = tonic::transport::Certificate::from_pem(cert_pem);
                 tls = tls.ca_certificate(ca);
             } else {
@@ -54,6 +146,10 @@ impl GrpcClient {
                 // Note: tonic doesn't support skipping verification directly
                 // We'll need to load a dummy cert or use HTTP instead
             }
+            // Presented when the server requires mutual TLS
+            if let Some(identity) = client_identity() {
+                tls = tls.identity(identity);
+            }
             
             Endpoint::from_shared(addr.to_string())?
                 .tls_config(tls)?
@@ -67,23 +163,7 @@ impl GrpcClient {
             
         let channel = endpoint.connect().await?;
         
-        Ok(Self {
-            user: UserServiceClient::new(channel.clone()),
-            credential: CredentialServiceClient::new(channel.clone()),
-            platform: PlatformServiceClient::new(channel.clone()),
-            command: CommandServiceClient::new(channel.clone()),
-            redeem: RedeemServiceClient::new(channel.clone()),
-            config: ConfigServiceClient::new(channel.clone()),
-            ai: AiServiceClient::new(channel.clone()),
-            plugin: PluginServiceClient::new(channel.clone()),
-            osc: OscServiceClient::new(channel.clone()),
-            twitch: TwitchServiceClient::new(channel.clone()),
-            discord: DiscordServiceClient::new(channel.clone()),
-            vrchat: VrChatServiceClient::new(channel.clone()),
-            autostart: AutostartServiceClient::new(channel.clone()),
-            obs: ObsServiceClient::new(channel.clone()),
-            pipeline: EventPipelineServiceClient::new(channel.clone()),
-        })
+        Ok(Self::from_channel(channel))
     }
     
     
@@ -109,22 +189,83 @@ impl GrpcClient {
             
         let channel = endpoint.connect().await?;
         
-        Ok(Self {
-            user: UserServiceClient::new(channel.clone()),
-            credential: CredentialServiceClient::new(channel.clone()),
-            platform: PlatformServiceClient::new(channel.clone()),
-            command: CommandServiceClient::new(channel.clone()),
-            redeem: RedeemServiceClient::new(channel.clone()),
-            config: ConfigServiceClient::new(channel.clone()),
-            ai: AiServiceClient::new(channel.clone()),
-            plugin: PluginServiceClient::new(channel.clone()),
-            osc: OscServiceClient::new(channel.clone()),
-            twitch: TwitchServiceClient::new(channel.clone()),
-            discord: DiscordServiceClient::new(channel.clone()),
-            vrchat: VrChatServiceClient::new(channel.clone()),
-            autostart: AutostartServiceClient::new(channel.clone()),
-            obs: ObsServiceClient::new(channel.clone()),
-            pipeline: EventPipelineServiceClient::new(channel.clone()),
-        })
+        Ok(Self::from_channel(channel))
     }
-}
\ No newline at end of file
+
+    fn from_channel(channel: Channel) -> Self {
+        let session = SessionInterceptor::default();
+        if let Some(token) = default_api_token() {
+            if let Ok(value) = format!("Bearer {}", token).parse() {
+                *session.authorization.write().unwrap() = Some(value);
+            }
+        }
+        Self {
+            user: UserServiceClient::with_interceptor(channel.clone(), session.clone()),
+            credential: CredentialServiceClient::with_interceptor(channel.clone(), session.clone()),
+            platform: PlatformServiceClient::with_interceptor(channel.clone(), session.clone()),
+            command: CommandServiceClient::with_interceptor(channel.clone(), session.clone()),
+            redeem: RedeemServiceClient::with_interceptor(channel.clone(), session.clone()),
+            config: ConfigServiceClient::with_interceptor(channel.clone(), session.clone()),
+            ai: AiServiceClient::with_interceptor(channel.clone(), session.clone()),
+            plugin: PluginServiceClient::with_interceptor(channel.clone(), session.clone()),
+            osc: OscServiceClient::with_interceptor(channel.clone(), session.clone()),
+            twitch: TwitchServiceClient::with_interceptor(channel.clone(), session.clone()),
+            discord: DiscordServiceClient::with_interceptor(channel.clone(), session.clone()),
+            vrchat: VrChatServiceClient::with_interceptor(channel.clone(), session.clone()),
+            autostart: AutostartServiceClient::with_interceptor(channel.clone(), session.clone()),
+            obs: ObsServiceClient::with_interceptor(channel.clone(), session.clone()),
+            pipeline: EventPipelineServiceClient::with_interceptor(channel.clone(), session.clone()),
+            chat_archive: ChatArchiveServiceClient::with_interceptor(channel.clone(), session.clone()),
+            giveaway: GiveawayServiceClient::with_interceptor(channel.clone(), session.clone()),
+            crowd_vote: CrowdVoteServiceClient::with_interceptor(channel.clone(), session.clone()),
+            protection: ProtectionServiceClient::with_interceptor(channel.clone(), session.clone()),
+            moderation_rules: ModerationRulesServiceClient::with_interceptor(channel.clone(), session.clone()),
+            emote_stats: EmoteStatsServiceClient::with_interceptor(channel.clone(), session.clone()),
+            emote_sets: EmoteSetServiceClient::with_interceptor(channel.clone(), session.clone()),
+            localization: LocalizationServiceClient::with_interceptor(channel.clone(), session.clone()),
+            events: EventStreamServiceClient::with_interceptor(channel.clone(), session.clone()),
+            ui_settings: UiSettingsServiceClient::with_interceptor(channel.clone(), session.clone()),
+            responders: ResponderServiceClient::with_interceptor(channel.clone(), session.clone()),
+            alerting: AlertingServiceClient::with_interceptor(channel.clone(), session.clone()),
+            health: HealthServiceClient::with_interceptor(channel.clone(), session.clone()),
+            sessions: StreamSessionServiceClient::with_interceptor(channel.clone(), session.clone()),
+            export: ExportServiceClient::with_interceptor(channel.clone(), session.clone()),
+            session,
+        }
+    }
+
+    /// Send subsequent requests to `workspace` (name or UUID); `None` returns to the default workspace.
+    pub fn set_workspace(&self, workspace: Option<&str>) -> Result<(), String> {
+        let value = match workspace {
+            Some(name) => Some(name.parse::<MetadataValue<Ascii>>()
+                .map_err(|_| format!("Invalid workspace name '{}'", name))?),
+            None => None,
+        };
+        *self.session.workspace.write().unwrap() = value;
+        Ok(())
+    }
+
+    /// The selected workspace, or `None` for the default workspace.
+    pub fn workspace(&self) -> Option<String> {
+        self.session.workspace.read().unwrap()
+            .as_ref()
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    }
+
+    /// Authenticate subsequent requests with `token`; `None` sends no token.
+    pub fn set_token(&self, token: Option<&str>) -> Result<(), String> {
+        let value = match token {
+            Some(token) => Some(format!("Bearer {}", token.trim()).parse::<MetadataValue<Ascii>>()
+                .map_err(|_| "API token contains invalid characters".to_string())?),
+            None => None,
+        };
+        *self.session.authorization.write().unwrap() = value;
+        Ok(())
+    }
+
+    /// Whether requests carry an API token.
+    pub fn has_token(&self) -> bool {
+        self.session.authorization.read().unwrap().is_some()
+    }
+}
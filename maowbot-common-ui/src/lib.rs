@@ -2,22 +2,25 @@ pub mod chat;
 pub mod grpc;
 pub mod grpc_client;
 pub mod process_manager;
+pub mod service;
 pub mod state;
 pub mod events;
 pub mod settings;
+pub mod settings_sync;
 pub mod commands;
 pub mod completion;
 
-pub use chat::{ChatState, ChatMessage, ChatEvent};
+pub use chat::{ChatState, ChatMessage, ChatEvent, ChatBadge, ChatEmoteRange, ChatReplyParent};
 pub use grpc::SharedGrpcClient;
 pub use grpc_client::GrpcClient;
-pub use process_manager::{ProcessManager, ProcessType, ProcessStatus};
+pub use process_manager::{ProcessManager, ProcessType, ProcessStatus, RestartPolicy};
 pub use state::{AppState, LayoutSection};
-pub use events::{UIEvent, AppEvent, ChatCommand};
+pub use events::{UIEvent, AppEvent, ChatCommand, ConnectionStatus, CrowdVoteTally, CrowdVoteOptionTally};
 pub use settings::{
     SettingsTab, ChatSide, StreamerListEntry, 
-    UISettings, AudioSettings, StreamOverlaySettings
+    UISettings, AudioSettings, StreamOverlaySettings, ControllerBindings, ControllerButton
 };
+pub use settings_sync::{SettingsSync, SharedSettings};
 pub use commands::{CommandResult, CommandError};
 
 use anyhow::Result;
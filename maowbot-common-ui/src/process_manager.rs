@@ -1,167 +1,449 @@
-use std::path::PathBuf;
-use std::process::Stdio;
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::process::{Child, Command};
-use tokio::sync::Mutex;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tracing::{info, error, debug, warn};
 use crate::AppEvent;
 
-#[derive(Debug, Clone, Copy)]
+/// Lines of stderr kept for crash reports.
+const STDERR_TAIL_LINES: usize = 100;
+/// Pause before restarting a crashed process, so a crash on startup doesn't spin.
+const RESTART_DELAY: Duration = Duration::from_secs(2);
+
+/// Locates the maowbot-server binary: next to the current executable first,
+/// then in the usual cargo target directories.
+pub fn find_server_executable() -> Option<PathBuf> {
+    let exe_name = if cfg!(windows) { "maowbot-server.exe" } else { "maowbot-server" };
+
+    let mut possible_paths = Vec::new();
+    // Same directory as current exe
+    if let Some(dir) = std::env::current_exe().ok().and_then(|p| p.parent().map(PathBuf::from)) {
+        possible_paths.push(dir.join(exe_name));
+    }
+    // Relative paths from working directory
+    possible_paths.extend([
+        PathBuf::from(format!("./target/debug/{}", exe_name)),
+        PathBuf::from(format!("./target/release/{}", exe_name)),
+        PathBuf::from(format!("../maowbot-server/target/debug/{}", exe_name)),
+        PathBuf::from(format!("../maowbot-server/target/release/{}", exe_name)),
+    ]);
+
+    possible_paths
+        .into_iter()
+        .find(|p| {
+            let exists = p.exists();
+            debug!("Checking server path: {:?} - exists: {}", p, exists);
+            exists
+        })
+}
+
+/// Locates the maowbot-overlay binary the same way as the server.
+fn find_overlay_executable() -> Option<PathBuf> {
+    let exe_name = if cfg!(windows) { "maowbot-overlay.exe" } else { "maowbot-overlay" };
+
+    let mut possible_paths = Vec::new();
+    // Same directory as current exe
+    if let Some(dir) = std::env::current_exe().ok().and_then(|p| p.parent().map(PathBuf::from)) {
+        possible_paths.push(dir.join(exe_name));
+    }
+    // Relative paths from working directory
+    possible_paths.extend([
+        PathBuf::from(format!("./target/debug/{}", exe_name)),
+        PathBuf::from(format!("./target/release/{}", exe_name)),
+        PathBuf::from(format!("../maowbot-overlay/target/debug/{}", exe_name)),
+        PathBuf::from(format!("../maowbot-overlay/target/release/{}", exe_name)),
+    ]);
+
+    possible_paths
+        .into_iter()
+        .find(|p| {
+            let exists = p.exists();
+            debug!("Checking overlay path: {:?} - exists: {}", p, exists);
+            exists
+        })
+}
+
+/// Where crash reports are written: `<local data dir>/maowbot/crash-reports`.
+pub fn crash_report_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("maowbot").join("crash-reports"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProcessType {
     Server,
     Overlay,
 }
 
+impl ProcessType {
+    pub fn name(self) -> &'static str {
+        match self {
+            ProcessType::Server => "server",
+            ProcessType::Overlay => "overlay",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ProcessStatus {
     pub running: bool,
     pub pid: Option<u32>,
+    /// Automatic restarts since the manager was created
+    pub restarts: u32,
+    pub last_crash_report: Option<PathBuf>,
 }
 
-pub struct ProcessManager {
-    server_process: Arc<Mutex<Option<Child>>>,
-    overlay_process: Arc<Mutex<Option<Child>>>,
-    event_tx: Option<crossbeam_channel::Sender<AppEvent>>,
+/// Whether a crashed process is started again. At most `max_restarts` restarts
+/// happen within any `window`; past that it stays down until started by hand.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    pub enabled: bool,
+    pub max_restarts: usize,
+    pub window: Duration,
 }
 
-impl ProcessManager {
-    pub fn new() -> Self {
-        Self {
-            server_process: Arc::new(Mutex::new(None)),
-            overlay_process: Arc::new(Mutex::new(None)),
-            event_tx: None,
-        }
-    }
-
-    pub fn with_event_sender(event_tx: crossbeam_channel::Sender<AppEvent>) -> Self {
+impl Default for RestartPolicy {
+    fn default() -> Self {
         Self {
-            server_process: Arc::new(Mutex::new(None)),
-            overlay_process: Arc::new(Mutex::new(None)),
-            event_tx: Some(event_tx),
+            enabled: true,
+            max_restarts: 3,
+            window: Duration::from_secs(600),
         }
     }
+}
 
-    /// Check if a process is running
-    pub async fn is_running(&self, process_type: ProcessType) -> bool {
-        let process = match process_type {
-            ProcessType::Server => &self.server_process,
-            ProcessType::Overlay => &self.overlay_process,
-        };
+/// One managed process: the child, the end of its stderr, and its restart history.
+struct Supervised {
+    kind: ProcessType,
+    child: Mutex<Option<Child>>,
+    /// Bumped by every start and stop; a watcher whose launch is no longer
+    /// current leaves the process alone
+    generation: AtomicU64,
+    stderr_tail: Arc<Mutex<VecDeque<String>>>,
+    policy: Mutex<RestartPolicy>,
+    /// When each automatic restart happened
+    restarts: Mutex<VecDeque<Instant>>,
+    restart_count: Mutex<u32>,
+    last_crash_report: Mutex<Option<PathBuf>>,
+    event_tx: Option<crossbeam_channel::Sender<AppEvent>>,
+}
 
-        let mut guard = process.lock().await;
-        if let Some(child) = guard.as_mut() {
-            match child.try_wait() {
-                Ok(Some(_)) => {
-                    // Process has exited
-                    *guard = None;
-                    false
-                }
-                Ok(None) => {
-                    // Still running
-                    true
-                }
-                Err(_) => false,
-            }
-        } else {
-            false
-        }
+impl Supervised {
+    fn new(kind: ProcessType, event_tx: Option<crossbeam_channel::Sender<AppEvent>>) -> Arc<Self> {
+        Arc::new(Self {
+            kind,
+            child: Mutex::new(None),
+            generation: AtomicU64::new(0),
+            stderr_tail: Arc::new(Mutex::new(VecDeque::new())),
+            policy: Mutex::new(RestartPolicy::default()),
+            restarts: Mutex::new(VecDeque::new()),
+            restart_count: Mutex::new(0),
+            last_crash_report: Mutex::new(None),
+            event_tx,
+        })
     }
 
-    /// Get process status
-    pub async fn get_status(&self, process_type: ProcessType) -> ProcessStatus {
-        let process = match process_type {
-            ProcessType::Server => &self.server_process,
-            ProcessType::Overlay => &self.overlay_process,
-        };
-
-        let guard = process.lock().await;
-        if let Some(child) = guard.as_ref() {
-            ProcessStatus {
-                running: true,
-                pid: child.id(),
-            }
-        } else {
-            ProcessStatus {
-                running: false,
-                pid: None,
-            }
+    fn send(&self, event: AppEvent) {
+        if let Some(tx) = &self.event_tx {
+            let _ = tx.send(event);
         }
     }
 
-    /// Start the server process
-    pub async fn start_server(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut guard = self.server_process.lock().await;
-        
-        if guard.is_some() {
-            info!("Server already running");
-            return Ok(());
-        }
+    /// Whether the child is alive. Exited children are left for the watcher
+    /// to find, so it can tell a crash from a stop.
+    fn is_running(&self) -> bool {
+        let mut guard = self.child.lock().unwrap();
+        matches!(guard.as_mut().map(|child| child.try_wait()), Some(Ok(None)))
+    }
 
-        info!("Starting maowbot-server...");
+    fn command(&self) -> Result<(PathBuf, Command), Box<dyn std::error::Error>> {
+        let path = match self.kind {
+            ProcessType::Server => find_server_executable()
+                .ok_or("Could not find maowbot-server executable")?,
+            ProcessType::Overlay => find_overlay_executable()
+                .ok_or("Could not find maowbot-overlay executable")?,
+        };
 
-        // Try to find the server executable
-        let exe_name = if cfg!(windows) { "maowbot-server.exe" } else { "maowbot-server" };
-        
-        let possible_paths = vec![
-            // Same directory as current exe
-            std::env::current_exe()?.parent().unwrap().join(exe_name),
-            // Relative paths from working directory
-            PathBuf::from(format!("./target/debug/{}", exe_name)),
-            PathBuf::from(format!("./target/release/{}", exe_name)),
-            PathBuf::from(format!("../maowbot-server/target/debug/{}", exe_name)),
-            PathBuf::from(format!("../maowbot-server/target/release/{}", exe_name)),
-        ];
-
-        let server_path = possible_paths
-            .into_iter()
-            .find(|p| {
-                let exists = p.exists();
-                debug!("Checking server path: {:?} - exists: {}", p, exists);
-                exists
-            })
-            .ok_or("Could not find maowbot-server executable")?;
-
-        info!("Starting server from: {:?}", server_path);
-
-        let mut cmd = Command::new(&server_path);
+        let mut cmd = Command::new(&path);
         cmd.stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        if self.kind == ProcessType::Overlay {
+            // Pass through environment variables
+            let grpc_url = std::env::var("MAOWBOT_GRPC_URL").unwrap_or_else(|_| "https://127.0.0.1:9999".to_string());
+            let grpc_pass = std::env::var("MAOWBOT_GRPC_PASSPHRASE").unwrap_or_default();
+            let grpc_ca = std::env::var("MAOWBOT_GRPC_CA").unwrap_or_else(|_| "certs/server.crt".to_string());
+
+            cmd.env("MAOWBOT_GRPC_URL", grpc_url)
+                .env("MAOWBOT_GRPC_PASSPHRASE", grpc_pass)
+                .env("MAOWBOT_GRPC_CA", grpc_ca);
+        }
+
         // Pass through any relevant environment variables
         if let Ok(log_level) = std::env::var("RUST_LOG") {
             cmd.env("RUST_LOG", log_level);
         }
 
+        Ok((path, cmd))
+    }
+
+    /// Launches the process and starts watching it. Does nothing if it's already running.
+    fn spawn(self: &Arc<Self>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut guard = self.child.lock().unwrap();
+        if matches!(guard.as_mut().map(|child| child.try_wait()), Some(Ok(None))) {
+            info!("{} already running", self.kind.name());
+            return Ok(());
+        }
+
+        let (path, mut cmd) = self.command()?;
+        info!("Starting {} from: {:?}", self.kind.name(), path);
+
         let mut child = cmd.spawn()?;
-        let pid = child.id();
-        info!("Server process started with PID: {:?}", pid);
+        info!("{} process started with PID: {:?}", self.kind.name(), child.id());
+        self.stderr_tail.lock().unwrap().clear();
 
         // Capture stdout
         if let Some(stdout) = child.stdout.take() {
+            let name = self.kind.name();
             tokio::spawn(async move {
                 let reader = BufReader::new(stdout);
                 let mut lines = reader.lines();
                 while let Ok(Some(line)) = lines.next_line().await {
-                    info!("[server] {}", line);
+                    info!("[{}] {}", name, line);
                 }
             });
         }
 
-        // Capture stderr
+        // Capture stderr, keeping the end of it for crash reports
         if let Some(stderr) = child.stderr.take() {
+            let name = self.kind.name();
+            let tail = self.stderr_tail.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stderr);
                 let mut lines = reader.lines();
                 while let Ok(Some(line)) = lines.next_line().await {
-                    error!("[server] {}", line);
+                    error!("[{}] {}", name, line);
+                    let mut tail = tail.lock().unwrap();
+                    if tail.len() >= STDERR_TAIL_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
                 }
             });
         }
 
         *guard = Some(child);
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
         drop(guard);
 
+        if self.kind == ProcessType::Overlay {
+            self.send(AppEvent::OverlayStatusChanged(true));
+        }
+        tokio::spawn(self.clone().watch(generation));
+        Ok(())
+    }
+
+    /// Waits for the child started as `generation` to exit. `stop` moves to
+    /// a new generation first, so anything found exited here ended on its own.
+    async fn watch(self: Arc<Self>, generation: u64) {
+        let status = loop {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+
+            let mut guard = self.child.lock().unwrap();
+            if self.generation.load(Ordering::SeqCst) != generation {
+                // Stopped on purpose, or started again
+                return;
+            }
+            let Some(child) = guard.as_mut() else {
+                return;
+            };
+            match child.try_wait() {
+                Ok(None) => continue,
+                Ok(Some(status)) => {
+                    *guard = None;
+                    break Some(status);
+                }
+                Err(e) => {
+                    error!("Error checking {} status: {}", self.kind.name(), e);
+                    *guard = None;
+                    break None;
+                }
+            }
+        };
+
+        if self.kind == ProcessType::Overlay {
+            self.send(AppEvent::OverlayStatusChanged(false));
+        }
+        if status.is_some_and(|s| s.success()) {
+            info!("{} process exited normally", self.kind.name());
+            return;
+        }
+
+        let exit = describe_exit(status);
+        let report = match self.write_crash_report(&exit) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                warn!("Could not write {} crash report: {}", self.kind.name(), e);
+                None
+            }
+        };
+        *self.last_crash_report.lock().unwrap() = report.clone();
+
+        let restarting = self.take_restart();
+        error!(
+            "{} crashed ({}){}",
+            self.kind.name(),
+            exit,
+            if restarting { "; restarting" } else { "" }
+        );
+        self.send(AppEvent::ProcessCrashed {
+            process: self.kind,
+            exit,
+            report,
+            restarting,
+        });
+
+        if restarting {
+            tokio::time::sleep(RESTART_DELAY).await;
+            if self.generation.load(Ordering::SeqCst) != generation {
+                info!("{} was stopped or started meanwhile; not restarting", self.kind.name());
+                return;
+            }
+            if let Err(e) = self.spawn() {
+                error!("Failed to restart {}: {}", self.kind.name(), e);
+            }
+        }
+    }
+
+    /// Records a restart if the policy allows another one.
+    fn take_restart(&self) -> bool {
+        self.take_restart_at(Instant::now())
+    }
+
+    fn take_restart_at(&self, now: Instant) -> bool {
+        let policy = self.policy.lock().unwrap().clone();
+        if !policy.enabled {
+            return false;
+        }
+        let mut restarts = self.restarts.lock().unwrap();
+        while restarts.front().is_some_and(|at| now.duration_since(*at) > policy.window) {
+            restarts.pop_front();
+        }
+        if restarts.len() >= policy.max_restarts {
+            return false;
+        }
+        restarts.push_back(now);
+        *self.restart_count.lock().unwrap() += 1;
+        true
+    }
+
+    fn write_crash_report(&self, exit: &str) -> std::io::Result<PathBuf> {
+        let dir = crash_report_dir()
+            .ok_or_else(|| std::io::Error::other("no local data directory"))?;
+        std::fs::create_dir_all(&dir)?;
+        let now = chrono::Local::now();
+        let path = dir.join(format!("{}-{}.log", self.kind.name(), now.format("%Y%m%d-%H%M%S")));
+        let tail: Vec<String> = self.stderr_tail.lock().unwrap().iter().cloned().collect();
+        write_report(&path, self.kind, exit, &now.to_rfc3339(), &tail)?;
+        Ok(path)
+    }
+}
+
+fn describe_exit(status: Option<ExitStatus>) -> String {
+    let Some(status) = status else {
+        return "exit status unknown".to_string();
+    };
+    if let Some(code) = status.code() {
+        return format!("exit code {}", code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("killed by signal {}", signal);
+        }
+    }
+    status.to_string()
+}
+
+fn write_report(path: &Path, kind: ProcessType, exit: &str, time: &str, stderr_tail: &[String]) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    writeln!(file, "maowbot {} crash report", kind.name())?;
+    writeln!(file, "Time: {}", time)?;
+    writeln!(file, "Exit: {}", exit)?;
+    writeln!(file)?;
+    writeln!(file, "--- last {} line(s) of stderr ---", stderr_tail.len())?;
+    for line in stderr_tail {
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Cheap to clone: clones share the supervised processes.
+#[derive(Clone)]
+pub struct ProcessManager {
+    server: Arc<Supervised>,
+    overlay: Arc<Supervised>,
+}
+
+impl ProcessManager {
+    pub fn new() -> Self {
+        Self {
+            server: Supervised::new(ProcessType::Server, None),
+            overlay: Supervised::new(ProcessType::Overlay, None),
+        }
+    }
+
+    pub fn with_event_sender(event_tx: crossbeam_channel::Sender<AppEvent>) -> Self {
+        Self {
+            server: Supervised::new(ProcessType::Server, Some(event_tx.clone())),
+            overlay: Supervised::new(ProcessType::Overlay, Some(event_tx)),
+        }
+    }
+
+    fn process(&self, process_type: ProcessType) -> &Arc<Supervised> {
+        match process_type {
+            ProcessType::Server => &self.server,
+            ProcessType::Overlay => &self.overlay,
+        }
+    }
+
+    /// Changes how crashes of `process_type` are handled from now on.
+    pub fn set_restart_policy(&self, process_type: ProcessType, policy: RestartPolicy) {
+        *self.process(process_type).policy.lock().unwrap() = policy;
+    }
+
+    /// Check if a process is running
+    pub async fn is_running(&self, process_type: ProcessType) -> bool {
+        self.process(process_type).is_running()
+    }
+
+    /// Get process status
+    pub async fn get_status(&self, process_type: ProcessType) -> ProcessStatus {
+        let process = self.process(process_type);
+        let pid = process.child.lock().unwrap().as_ref().and_then(|child| child.id());
+        ProcessStatus {
+            running: process.is_running(),
+            pid,
+            restarts: *process.restart_count.lock().unwrap(),
+            last_crash_report: process.last_crash_report.lock().unwrap().clone(),
+        }
+    }
+
+    /// Start the server process
+    pub async fn start_server(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.server.is_running() {
+            info!("Server already running");
+            return Ok(());
+        }
+
+        info!("Starting maowbot-server...");
+        self.server.spawn()?;
+
         // Wait for server to be ready by checking if we can connect
         info!("Waiting for server to be ready...");
         for i in 0..30 {
@@ -191,145 +473,21 @@ impl ProcessManager {
 
     /// Start the overlay process
     pub async fn start_overlay(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut guard = self.overlay_process.lock().await;
-        
-        if guard.is_some() {
-            info!("Overlay already running");
-            return Ok(());
-        }
-
         info!("Starting maowbot-overlay...");
-
-        // Try to find the overlay executable
-        let exe_name = if cfg!(windows) { "maowbot-overlay.exe" } else { "maowbot-overlay" };
-        
-        let possible_paths = vec![
-            // Same directory as current exe
-            std::env::current_exe()?.parent().unwrap().join(exe_name),
-            // Relative paths from working directory
-            PathBuf::from(format!("./target/debug/{}", exe_name)),
-            PathBuf::from(format!("./target/release/{}", exe_name)),
-            PathBuf::from(format!("../maowbot-overlay/target/debug/{}", exe_name)),
-            PathBuf::from(format!("../maowbot-overlay/target/release/{}", exe_name)),
-        ];
-
-        let overlay_path = possible_paths
-            .into_iter()
-            .find(|p| {
-                let exists = p.exists();
-                debug!("Checking overlay path: {:?} - exists: {}", p, exists);
-                exists
-            })
-            .ok_or("Could not find maowbot-overlay executable")?;
-
-        info!("Starting overlay from: {:?}", overlay_path);
-
-        let mut cmd = Command::new(&overlay_path);
-        
-        // Pass through environment variables
-        let grpc_url = std::env::var("MAOWBOT_GRPC_URL").unwrap_or_else(|_| "https://127.0.0.1:9999".to_string());
-        let grpc_pass = std::env::var("MAOWBOT_GRPC_PASSPHRASE").unwrap_or_default();
-        let grpc_ca = std::env::var("MAOWBOT_GRPC_CA").unwrap_or_else(|_| "certs/server.crt".to_string());
-
-        cmd.env("MAOWBOT_GRPC_URL", grpc_url)
-            .env("MAOWBOT_GRPC_PASSPHRASE", grpc_pass)
-            .env("MAOWBOT_GRPC_CA", grpc_ca)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        if let Ok(log_level) = std::env::var("RUST_LOG") {
-            cmd.env("RUST_LOG", log_level);
-        }
-
-        let mut child = cmd.spawn()?;
-        let pid = child.id();
-        info!("Overlay process started with PID: {:?}", pid);
-
-        // Capture stdout
-        if let Some(stdout) = child.stdout.take() {
-            tokio::spawn(async move {
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    info!("[overlay] {}", line);
-                }
-            });
-        }
-
-        // Capture stderr
-        if let Some(stderr) = child.stderr.take() {
-            tokio::spawn(async move {
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    error!("[overlay] {}", line);
-                }
-            });
-        }
-
-        *guard = Some(child);
-        drop(guard); // Release the lock before spawning monitor
-
-        // Send event if we have an event sender
-        if let Some(tx) = &self.event_tx {
-            let _ = tx.send(AppEvent::OverlayStatusChanged(true));
-        }
-
-        // Monitor the overlay process
-        let process_handle = self.overlay_process.clone();
-        let event_tx_clone = self.event_tx.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-                let mut proc_guard = process_handle.lock().await;
-                if let Some(child) = proc_guard.as_mut() {
-                    match child.try_wait() {
-                        Ok(Some(status)) => {
-                            // Process has exited
-                            info!("Overlay process exited with status: {:?}", status);
-                            if !status.success() {
-                                error!("Overlay exited with error code: {:?}", status.code());
-                            }
-                            *proc_guard = None;
-                            if let Some(tx) = &event_tx_clone {
-                                let _ = tx.send(AppEvent::OverlayStatusChanged(false));
-                            }
-                            break;
-                        }
-                        Ok(None) => {
-                            // Still running
-                            continue;
-                        }
-                        Err(e) => {
-                            error!("Error checking overlay status: {}", e);
-                            *proc_guard = None;
-                            if let Some(tx) = &event_tx_clone {
-                                let _ = tx.send(AppEvent::OverlayStatusChanged(false));
-                            }
-                            break;
-                        }
-                    }
-                } else {
-                    // No process to monitor
-                    break;
-                }
-            }
-        });
-        
-        Ok(())
+        self.overlay.spawn()
     }
 
     /// Stop a process
     pub async fn stop(&self, process_type: ProcessType) -> Result<(), Box<dyn std::error::Error>> {
-        let process = match process_type {
-            ProcessType::Server => &self.server_process,
-            ProcessType::Overlay => &self.overlay_process,
+        // A new generation tells the watcher this exit is on purpose
+        let child = {
+            let process = self.process(process_type);
+            let mut guard = process.child.lock().unwrap();
+            process.generation.fetch_add(1, Ordering::SeqCst);
+            guard.take()
         };
-
-        let mut guard = process.lock().await;
         
-        if let Some(mut child) = guard.take() {
+        if let Some(mut child) = child {
             info!("Stopping {:?} process", process_type);
             
             // Try graceful shutdown first on Windows
@@ -360,9 +518,7 @@ impl ProcessManager {
                     
                     // Send event if we have an event sender
                     if let ProcessType::Overlay = process_type {
-                        if let Some(tx) = &self.event_tx {
-                            let _ = tx.send(AppEvent::OverlayStatusChanged(false));
-                        }
+                        self.overlay.send(AppEvent::OverlayStatusChanged(false));
                     }
                 }
                 Err(e) => {
@@ -427,4 +583,58 @@ impl Drop for ProcessManager {
         // The TUI should explicitly call stop_all() if it wants to clean up
         debug!("ProcessManager dropped - child processes may continue running");
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restarts_stop_at_the_policy_limit() {
+        let process = Supervised::new(ProcessType::Overlay, None);
+        *process.policy.lock().unwrap() = RestartPolicy {
+            enabled: true,
+            max_restarts: 2,
+            window: Duration::from_secs(60),
+        };
+        assert!(process.take_restart());
+        assert!(process.take_restart());
+        assert!(!process.take_restart());
+        assert_eq!(*process.restart_count.lock().unwrap(), 2);
+
+        process.policy.lock().unwrap().enabled = false;
+        process.restarts.lock().unwrap().clear();
+        assert!(!process.take_restart());
+    }
+
+    #[test]
+    fn test_restarts_are_allowed_again_once_old_ones_leave_the_window() {
+        let process = Supervised::new(ProcessType::Server, None);
+        *process.policy.lock().unwrap() = RestartPolicy {
+            enabled: true,
+            max_restarts: 2,
+            window: Duration::from_secs(60),
+        };
+        let start = Instant::now();
+        assert!(process.take_restart_at(start));
+        assert!(process.take_restart_at(start + Duration::from_secs(10)));
+        assert!(!process.take_restart_at(start + Duration::from_secs(20)));
+        // The first restart is now more than a window old; the second isn't
+        assert!(process.take_restart_at(start + Duration::from_secs(61)));
+        assert!(!process.take_restart_at(start + Duration::from_secs(65)));
+        assert_eq!(*process.restart_count.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_gives_up_after_repeated_crashes() {
+        let process = Supervised::new(ProcessType::Server, None);
+        let start = Instant::now();
+        let policy = RestartPolicy::default();
+        // A process crashing every few seconds is restarted max_restarts times, then left down
+        let restarted = (0..10)
+            .filter(|i| process.take_restart_at(start + Duration::from_secs(5 * i)))
+            .count();
+        assert_eq!(restarted, policy.max_restarts);
+        assert_eq!(*process.restart_count.lock().unwrap() as usize, policy.max_restarts);
+        assert!(!process.take_restart_at(start + policy.window));
+    }
+}
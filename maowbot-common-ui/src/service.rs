@@ -0,0 +1,437 @@
+//! Registers maowbot-server with the OS service manager so it starts at boot:
+//! a systemd unit on Linux, a Service Control Manager entry on Windows.
+//!
+//! The server resolves `certs/`, `postgres/` and its restart state relative to
+//! its working directory, so the service is pinned to the directory the
+//! install command was run from.
+
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tracing::info;
+
+use crate::process_manager::find_server_executable;
+
+pub const SERVICE_NAME: &str = "maowbot";
+
+/// Where a systemd unit is installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceScope {
+    /// `~/.config/systemd/user`, started with lingering so no login is needed
+    User,
+    /// `/etc/systemd/system` (needs root); Windows services are always system-wide
+    System,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceInstallOptions {
+    pub scope: ServiceScope,
+    /// Passed to the server as `--db`; the server default is used when `None`
+    pub db_url: Option<String>,
+    /// Defaults to the located maowbot-server binary
+    pub server_path: Option<PathBuf>,
+    /// Defaults to the current directory
+    pub work_dir: Option<PathBuf>,
+    /// Account a Linux system-wide unit runs as; defaults to the user who ran `sudo`
+    pub run_as: Option<String>,
+}
+
+impl Default for ServiceInstallOptions {
+    fn default() -> Self {
+        Self {
+            scope: ServiceScope::User,
+            db_url: None,
+            server_path: None,
+            work_dir: None,
+            run_as: None,
+        }
+    }
+}
+
+pub struct ServiceManager;
+
+impl ServiceManager {
+    /// Installs and enables the service. It is not started, so it can't
+    /// collide with a server this UI already launched; use `start` for that.
+    pub async fn install(opts: &ServiceInstallOptions) -> Result<String, Box<dyn std::error::Error>> {
+        let server_path = match &opts.server_path {
+            Some(p) => p.clone(),
+            None => find_server_executable().ok_or("Could not find maowbot-server executable")?,
+        };
+        let server_path = std::fs::canonicalize(&server_path)?;
+        let work_dir = match &opts.work_dir {
+            Some(d) => std::fs::canonicalize(d)?,
+            None => std::env::current_dir()?,
+        };
+
+        let mut notes = Vec::new();
+        if !work_dir.join("certs").exists() {
+            notes.push(format!(
+                "No certs/ in {}; the server generates a new self-signed certificate on first start.",
+                work_dir.display()
+            ));
+        }
+        if opts.db_url.is_none() && !work_dir.join("postgres").join("bin").exists() {
+            notes.push(format!(
+                "No postgres/bin in {}; pass --db if the database lives elsewhere.",
+                work_dir.display()
+            ));
+        }
+
+        let mut out = platform::install(opts, &server_path, &work_dir).await?;
+        for note in notes {
+            out.push_str(&format!("\nNote: {}", note));
+        }
+        Ok(out)
+    }
+
+    pub async fn uninstall() -> Result<String, Box<dyn std::error::Error>> {
+        platform::uninstall().await
+    }
+
+    pub async fn start() -> Result<String, Box<dyn std::error::Error>> {
+        platform::control("start").await
+    }
+
+    pub async fn stop() -> Result<String, Box<dyn std::error::Error>> {
+        platform::control("stop").await
+    }
+
+    pub async fn status() -> Result<String, Box<dyn std::error::Error>> {
+        platform::status().await
+    }
+}
+
+/// Quotes one Windows command-line argument so `CommandLineToArgvW` reads it
+/// back verbatim: quotes are backslash-escaped, and backslashes are doubled
+/// only where they precede a quote (including the closing one).
+#[cfg_attr(not(windows), allow(dead_code))]
+fn quote_windows_arg(arg: &str) -> String {
+    let mut out = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                out.push_str(&"\\".repeat(backslashes * 2 + 1));
+                out.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                out.push_str(&"\\".repeat(backslashes));
+                out.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    out.push_str(&"\\".repeat(backslashes * 2));
+    out.push('"');
+    out
+}
+
+/// Runs a command, returning stdout or an error carrying stderr.
+async fn run(program: &str, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    info!("Running {} {}", program, args.join(" "));
+    let output = Command::new(program).args(args).output().await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let detail = if stderr.is_empty() { stdout } else { stderr };
+        Err(format!("{} {} failed: {}", program, args.join(" "), detail).into())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::*;
+
+    const UNIT_FILE: &str = "maowbot.service";
+
+    fn unit_path(scope: ServiceScope) -> Option<PathBuf> {
+        match scope {
+            ServiceScope::System => Some(PathBuf::from("/etc/systemd/system").join(UNIT_FILE)),
+            ServiceScope::User => dirs::config_dir().map(|d| d.join("systemd").join("user").join(UNIT_FILE)),
+        }
+    }
+
+    /// The scope the unit is currently installed in, system-wide first.
+    fn installed_scope() -> Option<ServiceScope> {
+        [ServiceScope::System, ServiceScope::User]
+            .into_iter()
+            .find(|s| unit_path(*s).map(|p| p.exists()).unwrap_or(false))
+    }
+
+    async fn systemctl(scope: ServiceScope, args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+        let mut full = Vec::with_capacity(args.len() + 1);
+        if scope == ServiceScope::User {
+            full.push("--user");
+        }
+        full.extend_from_slice(args);
+        run("systemctl", &full).await
+    }
+
+    /// Quotes one ExecStart word: systemd splits on whitespace and expands `%` specifiers.
+    fn quote(word: &str) -> String {
+        let escaped = word.replace('%', "%%");
+        if escaped.chars().any(|c| c.is_whitespace() || c == '"' || c == '\\') {
+            format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+        } else {
+            escaped
+        }
+    }
+
+    /// The account a system-wide unit runs as. `$USER` is `root` under `sudo`,
+    /// so the invoking user comes from `$SUDO_USER`; root itself is refused
+    /// unless asked for by name.
+    pub fn service_user(
+        run_as: Option<&str>,
+        sudo_user: Option<&str>,
+        user: Option<&str>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some(name) = run_as {
+            return Ok(name.to_string());
+        }
+        [sudo_user, user]
+            .into_iter()
+            .flatten()
+            .find(|u| !u.is_empty() && *u != "root")
+            .map(str::to_string)
+            .ok_or_else(|| "Refusing to run the system service as root; pass --user <account>".into())
+    }
+
+    pub fn render_unit(opts: &ServiceInstallOptions, server_path: &Path, work_dir: &Path) -> String {
+        let mut exec = vec![quote(&server_path.to_string_lossy()), "--mode".into(), "server".into()];
+        if let Some(db) = &opts.db_url {
+            exec.push("--db".into());
+            exec.push(quote(db));
+        }
+
+        let mut unit = String::new();
+        unit.push_str("[Unit]\n");
+        unit.push_str("Description=MaowBot streaming bot server\n");
+        unit.push_str("After=network-online.target\n");
+        unit.push_str("Wants=network-online.target\n\n");
+        unit.push_str("[Service]\n");
+        unit.push_str("Type=simple\n");
+        if opts.scope == ServiceScope::System {
+            if let Some(user) = &opts.run_as {
+                unit.push_str(&format!("User={}\n", user));
+            }
+        }
+        unit.push_str(&format!("WorkingDirectory={}\n", quote(&work_dir.to_string_lossy())));
+        unit.push_str(&format!("ExecStart={}\n", exec.join(" ")));
+        if let Ok(rust_log) = std::env::var("RUST_LOG") {
+            unit.push_str(&format!("Environment={}\n", quote(&format!("RUST_LOG={}", rust_log))));
+        }
+        // The server shuts down cleanly (and stops its Postgres) on Ctrl-C
+        unit.push_str("KillSignal=SIGINT\n");
+        unit.push_str("TimeoutStopSec=60\n");
+        unit.push_str("Restart=on-failure\n");
+        unit.push_str("RestartSec=5\n\n");
+        unit.push_str("[Install]\n");
+        unit.push_str(match opts.scope {
+            ServiceScope::User => "WantedBy=default.target\n",
+            ServiceScope::System => "WantedBy=multi-user.target\n",
+        });
+        unit
+    }
+
+    pub async fn install(opts: &ServiceInstallOptions, server_path: &Path, work_dir: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        let path = unit_path(opts.scope).ok_or("Could not determine the systemd user unit directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut opts = opts.clone();
+        if opts.scope == ServiceScope::System {
+            let sudo_user = std::env::var("SUDO_USER").ok();
+            let user = std::env::var("USER").ok();
+            opts.run_as = Some(service_user(opts.run_as.as_deref(), sudo_user.as_deref(), user.as_deref())?);
+        }
+        std::fs::write(&path, render_unit(&opts, server_path, work_dir))
+            .map_err(|e| format!("Failed to write {}: {} (system-wide installs need root)", path.display(), e))?;
+
+        systemctl(opts.scope, &["daemon-reload"]).await?;
+        systemctl(opts.scope, &["enable", UNIT_FILE]).await?;
+
+        let mut out = format!("Installed {} and enabled it at boot.", path.display());
+        if opts.scope == ServiceScope::User {
+            // Without lingering, user services only run while the user is logged in
+            let user = std::env::var("USER").unwrap_or_default();
+            if let Err(e) = run("loginctl", &["enable-linger", &user]).await {
+                out.push_str(&format!(
+                    "\nWarning: could not enable lingering ({}); the service will only run while {} is logged in.",
+                    e, user
+                ));
+            }
+        }
+        out.push_str("\nStop any server this UI started, then run `system service start`.");
+        Ok(out)
+    }
+
+    pub async fn uninstall() -> Result<String, Box<dyn std::error::Error>> {
+        let scope = installed_scope().ok_or("MaowBot service is not installed")?;
+        let path = unit_path(scope).ok_or("Could not determine the systemd unit directory")?;
+        // Disabling a unit that is already stopped/disabled is fine to ignore
+        let _ = systemctl(scope, &["disable", "--now", UNIT_FILE]).await;
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        systemctl(scope, &["daemon-reload"]).await?;
+        Ok(format!("Removed {}.", path.display()))
+    }
+
+    pub async fn control(action: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let scope = installed_scope().ok_or("MaowBot service is not installed (system install-service)")?;
+        systemctl(scope, &[action, UNIT_FILE]).await?;
+        Ok(format!("systemctl {} {}: ok", action, UNIT_FILE))
+    }
+
+    pub async fn status() -> Result<String, Box<dyn std::error::Error>> {
+        let Some(scope) = installed_scope() else {
+            return Ok("MaowBot service is not installed.".to_string());
+        };
+        let props = systemctl(scope, &[
+            "show", UNIT_FILE, "--property=ActiveState,SubState,UnitFileState,MainPID,ExecMainStartTimestamp",
+        ]).await?;
+        let mut out = format!("Service: {} ({} unit)\n", UNIT_FILE, if scope == ServiceScope::User { "user" } else { "system" });
+        for line in props.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                out.push_str(&format!("  {}: {}\n", key, if value.is_empty() { "-" } else { value }));
+            }
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::*;
+
+    fn path_str(path: &Path) -> Result<&str, Box<dyn std::error::Error>> {
+        path.to_str().ok_or_else(|| format!("Path is not valid UTF-8: {}", path.display()).into())
+    }
+
+    pub async fn install(opts: &ServiceInstallOptions, server_path: &Path, work_dir: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        // The server must be started in service mode to answer the Service Control Manager
+        let mut bin_path = format!(
+            "{} --mode service --work-dir {}",
+            quote_windows_arg(path_str(server_path)?),
+            quote_windows_arg(path_str(work_dir)?)
+        );
+        if let Some(db) = &opts.db_url {
+            bin_path.push_str(&format!(" --db {}", quote_windows_arg(db)));
+        }
+
+        run("sc.exe", &[
+            "create", SERVICE_NAME,
+            "binPath=", &bin_path,
+            "start=", "auto",
+            "DisplayName=", "MaowBot",
+        ]).await.map_err(|e| format!("{} (run the UI as Administrator)", e))?;
+        run("sc.exe", &["description", SERVICE_NAME, "MaowBot streaming bot server"]).await?;
+        // Restart after 5 seconds if the server crashes
+        run("sc.exe", &["failure", SERVICE_NAME, "reset=", "86400", "actions=", "restart/5000"]).await?;
+
+        Ok(format!(
+            "Registered Windows service '{}' (starts automatically at boot).\n\
+             Stop any server this UI started, then run `system service start`.",
+            SERVICE_NAME
+        ))
+    }
+
+    pub async fn uninstall() -> Result<String, Box<dyn std::error::Error>> {
+        let _ = run("sc.exe", &["stop", SERVICE_NAME]).await;
+        run("sc.exe", &["delete", SERVICE_NAME]).await?;
+        Ok(format!("Removed Windows service '{}'.", SERVICE_NAME))
+    }
+
+    pub async fn control(action: &str) -> Result<String, Box<dyn std::error::Error>> {
+        run("sc.exe", &[action, SERVICE_NAME]).await?;
+        Ok(format!("sc.exe {} {}: ok", action, SERVICE_NAME))
+    }
+
+    pub async fn status() -> Result<String, Box<dyn std::error::Error>> {
+        match run("sc.exe", &["query", SERVICE_NAME]).await {
+            Ok(out) => Ok(out),
+            Err(_) => Ok("MaowBot service is not installed.".to_string()),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+mod platform {
+    use super::*;
+
+    const UNSUPPORTED: &str = "Service install is only supported on Linux (systemd) and Windows";
+
+    pub async fn install(_: &ServiceInstallOptions, _: &Path, _: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub async fn uninstall() -> Result<String, Box<dyn std::error::Error>> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub async fn control(_: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Err(UNSUPPORTED.into())
+    }
+
+    pub async fn status() -> Result<String, Box<dyn std::error::Error>> {
+        Err(UNSUPPORTED.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_windows_arg() {
+        assert_eq!(quote_windows_arg(r"C:\Program Files\maowbot"), r#""C:\Program Files\maowbot""#);
+        assert_eq!(quote_windows_arg(r"C:\maowbot\"), r#""C:\maowbot\\""#);
+        assert_eq!(quote_windows_arg(r#"postgres://u:p"a@h/db"#), r#""postgres://u:p\"a@h/db""#);
+        assert_eq!(quote_windows_arg(r#"a\"b"#), r#""a\\\"b""#);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_service_user_prefers_sudo_user_over_root() {
+        let user = platform::service_user(None, Some("kitty"), Some("root")).unwrap();
+        assert_eq!(user, "kitty");
+        assert_eq!(platform::service_user(Some("maow"), Some("kitty"), Some("root")).unwrap(), "maow");
+        assert_eq!(platform::service_user(None, None, Some("kitty")).unwrap(), "kitty");
+        assert!(platform::service_user(None, None, Some("root")).is_err());
+        assert!(platform::service_user(None, Some("root"), Some("root")).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_render_unit_system_scope_sets_user() {
+        let opts = ServiceInstallOptions {
+            scope: ServiceScope::System,
+            run_as: Some("kitty".into()),
+            ..Default::default()
+        };
+        let unit = platform::render_unit(&opts, Path::new("/opt/maowbot/maowbot-server"), Path::new("/srv/maowbot"));
+        assert!(unit.contains("User=kitty\n"));
+        assert!(unit.contains("WantedBy=multi-user.target"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_render_unit_quotes_paths_and_db() {
+        let opts = ServiceInstallOptions {
+            scope: ServiceScope::User,
+            db_url: Some("postgres://maow@localhost:5432/maowbot".into()),
+            ..Default::default()
+        };
+        let unit = platform::render_unit(&opts, Path::new("/opt/maow bot/maowbot-server"), Path::new("/srv/maowbot"));
+        assert!(unit.contains("WorkingDirectory=/srv/maowbot\n"));
+        assert!(unit.contains(
+            "ExecStart=\"/opt/maow bot/maowbot-server\" --mode server --db postgres://maow@localhost:5432/maowbot\n"
+        ));
+        assert!(unit.contains("WantedBy=default.target"));
+        assert!(!unit.contains("User="));
+    }
+}
@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SettingsTab {
     Connection,
     General,
@@ -13,13 +14,13 @@ pub enum SettingsTab {
     About,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ChatSide {
     Left,
     Right,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StreamerListEntry {
     pub id: String,
     pub name: String,
@@ -27,7 +28,8 @@ pub struct StreamerListEntry {
     pub enabled: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UISettings {
     pub main_stream_chat_side: ChatSide,
     pub secondary_stream_chat_side: ChatSide,
@@ -40,7 +42,8 @@ pub struct UISettings {
     pub tab_enabled: HashMap<SettingsTab, bool>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AudioSettings {
     pub master_volume: f32,
     pub alert_volume: f32,
@@ -50,7 +53,8 @@ pub struct AudioSettings {
     pub audio_device: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StreamOverlaySettings {
     pub show_chat: bool,
     pub chat_opacity: f32,
@@ -61,6 +65,74 @@ pub struct StreamOverlaySettings {
     pub show_alerts: bool,
     pub alert_position: String,
     pub alert_duration: f32,
+    pub controller: ControllerBindings,
+}
+
+/// A controller button the VR overlay can bind an action to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControllerButton {
+    Disabled,
+    Menu,
+    Grip,
+    /// A on Index, X/A on Touch controllers
+    A,
+    /// Pressing the thumbstick or trackpad
+    Thumbstick,
+}
+
+impl ControllerButton {
+    /// In the order the dashboard lists them.
+    pub const ALL: [ControllerButton; 5] = [
+        ControllerButton::Disabled,
+        ControllerButton::Menu,
+        ControllerButton::Grip,
+        ControllerButton::A,
+        ControllerButton::Thumbstick,
+    ];
+
+    /// The OpenVR `EVRButtonId`, or `None` when disabled.
+    pub fn openvr_id(self) -> Option<u32> {
+        match self {
+            ControllerButton::Disabled => None,
+            ControllerButton::Menu => Some(1),
+            ControllerButton::Grip => Some(2),
+            ControllerButton::A => Some(7),
+            ControllerButton::Thumbstick => Some(32),
+        }
+    }
+
+    /// Position in `ALL`, as the dashboard's combo boxes use.
+    pub fn index(self) -> i32 {
+        Self::ALL.iter().position(|b| *b == self).unwrap_or(0) as i32
+    }
+
+    pub fn from_index(index: i32) -> Self {
+        usize::try_from(index).ok()
+            .and_then(|i| Self::ALL.get(i).copied())
+            .unwrap_or(ControllerButton::Disabled)
+    }
+}
+
+/// What the controllers do in the VR overlay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControllerBindings {
+    /// Shows or hides the virtual keyboard
+    pub keyboard_button: ControllerButton,
+    /// Opens the maowbot page in the SteamVR dashboard
+    pub dashboard_button: ControllerButton,
+    /// How far the trigger goes down before it counts as a click (0-1)
+    pub trigger_threshold: f32,
+}
+
+impl Default for ControllerBindings {
+    fn default() -> Self {
+        Self {
+            keyboard_button: ControllerButton::Menu,
+            dashboard_button: ControllerButton::Disabled,
+            trigger_threshold: 0.5,
+        }
+    }
 }
 
 impl Default for UISettings {
@@ -126,6 +198,36 @@ impl Default for StreamOverlaySettings {
             show_alerts: true,
             alert_position: "Top Center".to_string(),
             alert_duration: 5.0,
+            controller: ControllerBindings::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_controller_buttons_round_trip_through_dashboard_index() {
+        for button in ControllerButton::ALL {
+            assert_eq!(ControllerButton::from_index(button.index()), button);
         }
+        assert_eq!(ControllerButton::from_index(-1), ControllerButton::Disabled);
+        assert_eq!(ControllerButton::from_index(99), ControllerButton::Disabled);
+        assert_eq!(ControllerButton::Disabled.openvr_id(), None);
+        assert_eq!(ControllerButton::Menu.openvr_id(), Some(1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_overlay_settings_saved_before_bindings_get_defaults() {
+        let old = r#"{"show_chat":false,"chat_opacity":0.5}"#;
+        let settings: StreamOverlaySettings = serde_json::from_str(old).unwrap();
+        assert!(!settings.show_chat);
+        assert_eq!(settings.controller, ControllerBindings::default());
+        assert_eq!(settings.controller.keyboard_button, ControllerButton::Menu);
+
+        let partial: ControllerBindings = serde_json::from_str(r#"{"dashboard_button":"Grip"}"#).unwrap();
+        assert_eq!(partial.dashboard_button, ControllerButton::Grip);
+        assert_eq!(partial.trigger_threshold, 0.5);
+    }
+}
@@ -0,0 +1,198 @@
+//! Keeps the settings the GUI and the VR overlay share in step through the
+//! server's UiSettingsService. Local edits are batched up and saved; changes
+//! saved by any other client arrive as `AppEvent::SettingsChanged`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use crossbeam_channel::Sender;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::time::{sleep_until, Instant};
+use tonic::Code;
+use maowbot_proto::maowbot::services::{
+    ui_settings_service_client::UiSettingsServiceClient,
+    StreamUiSettingsRequest, UpdateUiSettingsRequest,
+};
+
+use crate::events::AppEvent;
+use crate::grpc_client::{GrpcClient, ScopedChannel};
+use crate::settings::{AudioSettings, StreamOverlaySettings, UISettings};
+
+/// How long local edits settle before they're saved, so dragging a slider saves once.
+const PUBLISH_DELAY: Duration = Duration::from_millis(300);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// One section of the shared settings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SharedSettings {
+    Ui(UISettings),
+    Audio(AudioSettings),
+    Overlay(StreamOverlaySettings),
+}
+
+impl SharedSettings {
+    /// The section the server stores these settings under.
+    pub fn section(&self) -> &'static str {
+        match self {
+            SharedSettings::Ui(_) => "ui",
+            SharedSettings::Audio(_) => "audio",
+            SharedSettings::Overlay(_) => "overlay",
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        match self {
+            SharedSettings::Ui(s) => serde_json::to_string(s),
+            SharedSettings::Audio(s) => serde_json::to_string(s),
+            SharedSettings::Overlay(s) => serde_json::to_string(s),
+        }
+    }
+
+    /// Parses a section from the server; fields it lacks keep their defaults.
+    /// Returns `None` for sections this client doesn't know.
+    pub fn from_json(section: &str, json: &str) -> Option<serde_json::Result<Self>> {
+        Some(match section {
+            "ui" => serde_json::from_str(json).map(SharedSettings::Ui),
+            "audio" => serde_json::from_str(json).map(SharedSettings::Audio),
+            "overlay" => serde_json::from_str(json).map(SharedSettings::Overlay),
+            _ => return None,
+        })
+    }
+}
+
+/// Handle for saving local changes; dropping every clone stops the saving
+/// but not the receiving.
+#[derive(Clone)]
+pub struct SettingsSync {
+    local_tx: UnboundedSender<SharedSettings>,
+}
+
+impl SettingsSync {
+    pub fn start(client_name: &str, url: String, event_tx: Sender<AppEvent>) -> Self {
+        let (local_tx, local_rx) = unbounded_channel();
+        let mut task = SyncTask {
+            url,
+            // Unique per process so a restarted client still applies what it saved last time
+            client_id: format!("{}-{}", client_name, uuid::Uuid::new_v4()),
+            event_tx,
+            local_rx,
+            local_closed: false,
+            pending: HashMap::new(),
+        };
+
+        tokio::spawn(async move {
+            loop {
+                match task.connect_and_run().await {
+                    Ok(()) => tracing::info!("Settings sync stream closed"),
+                    Err(e) => tracing::warn!("Settings sync error: {}", e),
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+
+        Self { local_tx }
+    }
+
+    /// Saves `settings` on the server, which passes them on to the other clients.
+    pub fn publish(&self, settings: SharedSettings) {
+        let _ = self.local_tx.send(settings);
+    }
+}
+
+struct SyncTask {
+    url: String,
+    client_id: String,
+    event_tx: Sender<AppEvent>,
+    local_rx: UnboundedReceiver<SharedSettings>,
+    local_closed: bool,
+    /// Latest unsaved edit per section; kept across reconnects
+    pending: HashMap<&'static str, SharedSettings>,
+}
+
+impl SyncTask {
+    async fn connect_and_run(&mut self) -> anyhow::Result<()> {
+        let client = GrpcClient::connect(&self.url).await
+            .map_err(|e| anyhow::anyhow!("connecting to {}: {}", self.url, e))?;
+        let mut service = client.ui_settings.clone();
+
+        // Edits made while disconnected go in before the server's copy comes back
+        self.flush(&mut service).await?;
+        let mut stream = service
+            .stream_ui_settings(StreamUiSettingsRequest { sections: vec![] })
+            .await?
+            .into_inner();
+        tracing::info!("Settings sync connected to {}", self.url);
+
+        let mut flush_at: Option<Instant> = None;
+        loop {
+            tokio::select! {
+                message = stream.message() => {
+                    let Some(section) = message? else { return Ok(()) };
+                    if section.updated_by == self.client_id {
+                        continue;
+                    }
+                    match SharedSettings::from_json(&section.section, &section.settings_json) {
+                        Some(Ok(settings)) => {
+                            let _ = self.event_tx.send(AppEvent::SettingsChanged(settings));
+                        }
+                        Some(Err(e)) => tracing::warn!("Ignoring unreadable '{}' settings: {}", section.section, e),
+                        None => {}
+                    }
+                }
+                local = self.local_rx.recv(), if !self.local_closed => match local {
+                    Some(settings) => {
+                        self.pending.insert(settings.section(), settings);
+                        flush_at = Some(Instant::now() + PUBLISH_DELAY);
+                    }
+                    None => self.local_closed = true,
+                },
+                _ = sleep_until(flush_at.unwrap_or_else(Instant::now)), if flush_at.is_some() => {
+                    flush_at = None;
+                    self.flush(&mut service).await?;
+                }
+            }
+        }
+    }
+
+    /// Saves the pending edits. Ones the server refuses are dropped; the rest
+    /// stay pending when the connection fails.
+    async fn flush(&mut self, service: &mut UiSettingsServiceClient<ScopedChannel>) -> anyhow::Result<()> {
+        let sections: Vec<_> = self.pending.keys().copied().collect();
+        for section in sections {
+            let request = UpdateUiSettingsRequest {
+                section: section.to_string(),
+                settings_json: self.pending[section].to_json()?,
+                client_id: self.client_id.clone(),
+            };
+            match service.update_ui_settings(request).await {
+                Ok(_) => {}
+                Err(status) if matches!(
+                    status.code(),
+                    Code::InvalidArgument | Code::PermissionDenied | Code::Unauthenticated
+                ) => {
+                    tracing::warn!("Server refused '{}' settings: {}", section, status.message());
+                }
+                Err(status) => return Err(status.into()),
+            }
+            self.pending.remove(section);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_missing_fields_fall_back_to_defaults() {
+        let parsed = SharedSettings::from_json("overlay", r#"{"chat_opacity":0.5}"#).unwrap().unwrap();
+        assert_eq!(parsed, SharedSettings::Overlay(StreamOverlaySettings {
+            chat_opacity: 0.5,
+            ..StreamOverlaySettings::default()
+        }));
+
+        let ui = SharedSettings::Ui(UISettings::default());
+        assert_eq!(SharedSettings::from_json("ui", &ui.to_json().unwrap()).unwrap().unwrap(), ui);
+        assert!(SharedSettings::from_json("keyboard", "{}").is_none());
+    }
+}
@@ -1,12 +1,15 @@
 use std::sync::{Arc, Mutex};
 use crate::chat::ChatState;
+use crate::events::ConnectionStatus;
 
 #[derive(Clone)]
 pub struct AppState {
     pub chat_state: Arc<Mutex<ChatState>>,
     pub secondary_chat_state: Arc<Mutex<ChatState>>,
     pub overlay_running: Arc<Mutex<bool>>,
-    pub grpc_connected: Arc<Mutex<bool>>,
+    /// Latest crash of a managed process or desktop ops alert, shown until dismissed
+    pub process_notice: Arc<Mutex<Option<String>>>,
+    pub grpc_status: Arc<Mutex<ConnectionStatus>>,
     pub active_tab: Arc<Mutex<String>>,
     pub layout_order: Arc<Mutex<Vec<LayoutSection>>>,
     pub is_docked: Arc<Mutex<bool>>,
@@ -20,13 +23,20 @@ pub enum LayoutSection {
     RightPanel,
 }
 
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AppState {
     pub fn new() -> Self {
         Self {
             chat_state: Arc::new(Mutex::new(ChatState::new())),
             secondary_chat_state: Arc::new(Mutex::new(ChatState::new())),
             overlay_running: Arc::new(Mutex::new(false)),
-            grpc_connected: Arc::new(Mutex::new(false)),
+            process_notice: Arc::new(Mutex::new(None)),
+            grpc_status: Arc::new(Mutex::new(ConnectionStatus::Connecting { attempt: 1 })),
             active_tab: Arc::new(Mutex::new("Multiview".to_string())),
             layout_order: Arc::new(Mutex::new(vec![
                 LayoutSection::LeftChat,
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use maowbot_common_ui::commands::pipeline::PipelineSpec;
+
+    #[test]
+    fn test_parse_toml_spec() {
+        let spec = PipelineSpec::parse(r#"
+name = "welcome"
+priority = 50
+
+[[filters]]
+type = "platform_filter"
+config = { platforms = ["twitch-irc"] }
+
+[[actions]]
+type = "twitch_message"
+config = { message_template = "Welcome {user}!" }
+continue_on_error = true
+"#, "toml").expect("valid spec");
+
+        assert_eq!(spec.name, "welcome");
+        assert_eq!(spec.priority, 50);
+        assert_eq!(spec.enabled, None);
+        assert_eq!(spec.filters[0].filter_type, "platform_filter");
+        assert_eq!(spec.filters[0].config["platforms"][0], "twitch-irc");
+        assert!(spec.actions[0].continue_on_error);
+        assert_eq!(spec.actions[0].retry_delay_ms, 1000);
+    }
+
+    #[test]
+    fn test_parse_json_spec_defaults() {
+        let spec = PipelineSpec::parse(r#"{"name": "empty", "enabled": false}"#, "json")
+            .expect("valid spec");
+
+        assert_eq!(spec.priority, 100);
+        assert_eq!(spec.enabled, Some(false));
+        assert!(spec.filters.is_empty());
+        assert!(spec.actions.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_name() {
+        assert!(PipelineSpec::parse("priority = 1", "toml").is_err());
+        assert!(PipelineSpec::parse("{}", "yaml").is_err());
+    }
+}
@@ -55,11 +55,12 @@ pub enum Error {
     #[error("Tonic transport error: {0}")]
     Tonic(#[from] tonic::transport::Error),
 
+    // Boxed, like GrpcStatus, so every `Result<_, Error>` stays small
     #[error("MPSC send error: {0}")]
-    MpscSend(#[from] tokio::sync::mpsc::error::SendError<maowbot_proto::plugs::PluginStreamRequest>),
+    MpscSend(Box<tokio::sync::mpsc::error::SendError<maowbot_proto::plugs::PluginStreamRequest>>),
 
     #[error("gRPC status error: {0}")]
-    GrpcStatus(#[from] tonic::Status),
+    GrpcStatus(Box<tonic::Status>),
 
     #[error("Migration error: {0}")]
     Migration(#[from] sqlx::migrate::MigrateError),
@@ -100,6 +101,18 @@ pub enum Error {
     ServiceError(String),
 }
 
+impl From<tokio::sync::mpsc::error::SendError<maowbot_proto::plugs::PluginStreamRequest>> for Error {
+    fn from(e: tokio::sync::mpsc::error::SendError<maowbot_proto::plugs::PluginStreamRequest>) -> Self {
+        Error::MpscSend(Box::new(e))
+    }
+}
+
+impl From<tonic::Status> for Error {
+    fn from(status: tonic::Status) -> Self {
+        Error::GrpcStatus(Box::new(status))
+    }
+}
+
 impl From<String> for Error {
     fn from(s: String) -> Self {
         Error::Parse(s)
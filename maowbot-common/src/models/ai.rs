@@ -3,8 +3,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
 use uuid::Uuid;
-use std::collections::HashMap;
 
 /// Represents an AI provider
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -150,13 +150,13 @@ pub enum ActionHandlerType {
     Command,   // Bot command
 }
 
-impl ToString for ActionHandlerType {
-    fn to_string(&self) -> String {
+impl fmt::Display for ActionHandlerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ActionHandlerType::Function => "function".to_string(),
-            ActionHandlerType::Plugin => "plugin".to_string(),
-            ActionHandlerType::Webhook => "webhook".to_string(),
-            ActionHandlerType::Command => "command".to_string(),
+            ActionHandlerType::Function => write!(f, "function"),
+            ActionHandlerType::Plugin => write!(f, "plugin"),
+            ActionHandlerType::Webhook => write!(f, "webhook"),
+            ActionHandlerType::Command => write!(f, "command"),
         }
     }
 }
@@ -254,14 +254,14 @@ pub enum TriggerType {
     Condition,
 }
 
-impl ToString for TriggerType {
-    fn to_string(&self) -> String {
+impl fmt::Display for TriggerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TriggerType::Prefix => "prefix".to_string(),
-            TriggerType::Regex => "regex".to_string(),
-            TriggerType::Mention => "mention".to_string(),
-            TriggerType::Schedule => "schedule".to_string(),
-            TriggerType::Condition => "condition".to_string(),
+            TriggerType::Prefix => write!(f, "prefix"),
+            TriggerType::Regex => write!(f, "regex"),
+            TriggerType::Mention => write!(f, "mention"),
+            TriggerType::Schedule => write!(f, "schedule"),
+            TriggerType::Condition => write!(f, "condition"),
         }
     }
 }
@@ -327,13 +327,13 @@ pub enum MemoryRole {
     Function,
 }
 
-impl ToString for MemoryRole {
-    fn to_string(&self) -> String {
+impl fmt::Display for MemoryRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            MemoryRole::System => "system".to_string(),
-            MemoryRole::User => "user".to_string(),
-            MemoryRole::Assistant => "assistant".to_string(),
-            MemoryRole::Function => "function".to_string(),
+            MemoryRole::System => write!(f, "system"),
+            MemoryRole::User => write!(f, "user"),
+            MemoryRole::Assistant => write!(f, "assistant"),
+            MemoryRole::Function => write!(f, "function"),
         }
     }
 }
@@ -0,0 +1,294 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// How bad an operational problem is. Ordered, so rules can ask for "warning
+/// and up".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    /// Also goes out during quiet hours
+    Critical,
+}
+
+impl fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertSeverity::Info => write!(f, "info"),
+            AlertSeverity::Warning => write!(f, "warning"),
+            AlertSeverity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl FromStr for AlertSeverity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(AlertSeverity::Info),
+            "warning" | "warn" => Ok(AlertSeverity::Warning),
+            "critical" | "crit" => Ok(AlertSeverity::Critical),
+            other => Err(Error::Parse(format!("Unknown severity '{}', expected info, warning or critical", other))),
+        }
+    }
+}
+
+/// What went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlertKind {
+    /// A platform connection is reconnecting over and over, or gave up
+    PlatformDisconnect,
+    /// An OAuth token or VRChat session could not be renewed
+    CredentialFailure,
+    /// Many pipeline executions failed recently
+    PipelineFailures,
+    /// Little free space left where the bot keeps its data
+    LowDisk,
+    /// The moderation ladder timed out or banned someone
+    Moderation,
+}
+
+impl AlertKind {
+    pub const ALL: [AlertKind; 5] = [
+        AlertKind::PlatformDisconnect,
+        AlertKind::CredentialFailure,
+        AlertKind::PipelineFailures,
+        AlertKind::LowDisk,
+        AlertKind::Moderation,
+    ];
+}
+
+impl fmt::Display for AlertKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertKind::PlatformDisconnect => write!(f, "platform_disconnect"),
+            AlertKind::CredentialFailure => write!(f, "credential_failure"),
+            AlertKind::PipelineFailures => write!(f, "pipeline_failures"),
+            AlertKind::LowDisk => write!(f, "low_disk"),
+            AlertKind::Moderation => write!(f, "moderation"),
+        }
+    }
+}
+
+impl FromStr for AlertKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "platform_disconnect" | "platform" | "disconnect" => Ok(AlertKind::PlatformDisconnect),
+            "credential_failure" | "credential" | "credentials" => Ok(AlertKind::CredentialFailure),
+            "pipeline_failures" | "pipeline" | "pipelines" => Ok(AlertKind::PipelineFailures),
+            "low_disk" | "disk" => Ok(AlertKind::LowDisk),
+            "moderation" | "mod" | "automod" => Ok(AlertKind::Moderation),
+            other => Err(Error::Parse(format!(
+                "Unknown alert kind '{}', expected platform, credential, pipeline, disk or moderation", other
+            ))),
+        }
+    }
+}
+
+/// Where a rule sends the alerts it matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlertRoute {
+    /// A Discord direct message to the user id in the rule's target
+    DiscordDm,
+    /// A Twitch chat message in the target channel, or the broadcaster's
+    Chat,
+    /// A notification in the GUI
+    Desktop,
+}
+
+impl fmt::Display for AlertRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertRoute::DiscordDm => write!(f, "discord_dm"),
+            AlertRoute::Chat => write!(f, "chat"),
+            AlertRoute::Desktop => write!(f, "desktop"),
+        }
+    }
+}
+
+impl FromStr for AlertRoute {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "discord_dm" | "dm" | "discord" => Ok(AlertRoute::DiscordDm),
+            "chat" | "twitch" => Ok(AlertRoute::Chat),
+            "desktop" | "gui" => Ok(AlertRoute::Desktop),
+            other => Err(Error::Parse(format!("Unknown route '{}', expected dm, chat or desktop", other))),
+        }
+    }
+}
+
+/// A daily time span, written "23:00-08:00"; it may cross midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || Error::Parse(format!("Quiet hours must look like 23:00-08:00, not '{}'", s));
+        let (start, end) = s.trim().split_once('-').ok_or_else(bad)?;
+        let time = |t: &str| NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|_| bad());
+        let hours = QuietHours { start: time(start)?, end: time(end)? };
+        if hours.start == hours.end {
+            return Err(bad());
+        }
+        Ok(hours)
+    }
+}
+
+/// An operational problem, as raised by the alerting service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpsAlert {
+    pub kind: AlertKind,
+    pub severity: AlertSeverity,
+    /// What the alert is about, e.g. "twitch-irc:mybot"; repeats of the same
+    /// key are deduplicated
+    pub key: String,
+    pub title: String,
+    pub message: String,
+    pub raised_at: DateTime<Utc>,
+}
+
+/// Routes matching alerts to one destination.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub rule_id: Uuid,
+    /// Unique
+    pub name: String,
+    /// The kinds this rule handles; empty for every kind
+    pub kinds: Vec<AlertKind>,
+    pub min_severity: AlertSeverity,
+    pub route: AlertRoute,
+    /// Discord user id for `DiscordDm`, Twitch channel for `Chat` (None for
+    /// the broadcaster's); unused for `Desktop`
+    pub target: Option<String>,
+    /// The same alert (kind and key) isn't sent again by this rule for this long
+    pub dedupe_secs: i32,
+    /// Alerts below critical are dropped by this rule during these hours,
+    /// in the `alerting.timezone` zone
+    pub quiet_hours: Option<QuietHours>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AlertRule {
+    pub fn matches(&self, alert: &OpsAlert) -> bool {
+        self.enabled
+            && alert.severity >= self.min_severity
+            && (self.kinds.is_empty() || self.kinds.contains(&alert.kind))
+    }
+
+    /// Whether an alert matched by this rule is held back at `local_time`.
+    pub fn is_quiet(&self, alert: &OpsAlert, local_time: NaiveTime) -> bool {
+        alert.severity < AlertSeverity::Critical
+            && self.quiet_hours.is_some_and(|quiet| quiet.contains(local_time))
+    }
+
+    /// Checks the target fits the route.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.dedupe_secs < 0 {
+            return Err(Error::Parse("The dedupe window can't be negative".into()));
+        }
+        match (self.route, self.target.as_deref()) {
+            (AlertRoute::DiscordDm, Some(id)) if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) => Ok(()),
+            (AlertRoute::DiscordDm, _) => Err(Error::Parse("Discord DMs need the recipient's Discord user id as target".into())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    fn alert(kind: AlertKind, severity: AlertSeverity) -> OpsAlert {
+        OpsAlert {
+            kind,
+            severity,
+            key: "twitch-irc:bot".into(),
+            title: "Twitch IRC disconnected".into(),
+            message: String::new(),
+            raised_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_can_cross_midnight() {
+        let night: QuietHours = "23:00-08:00".parse().unwrap();
+        assert!(night.contains(time("23:30")));
+        assert!(night.contains(time("03:00")));
+        assert!(!night.contains(time("08:00")));
+        assert!(!night.contains(time("12:00")));
+        assert_eq!(night.to_string(), "23:00-08:00");
+
+        let lunch: QuietHours = "12:00-13:00".parse().unwrap();
+        assert!(lunch.contains(time("12:30")));
+        assert!(!lunch.contains(time("13:30")));
+
+        assert!("12:00".parse::<QuietHours>().is_err());
+        assert!("12:00-12:00".parse::<QuietHours>().is_err());
+    }
+
+    #[test]
+    fn test_rules_match_kind_and_severity_and_let_critical_through_quiet_hours() {
+        let now = Utc::now();
+        let rule = AlertRule {
+            rule_id: Uuid::new_v4(),
+            name: "dm".into(),
+            kinds: vec![AlertKind::PlatformDisconnect],
+            min_severity: AlertSeverity::Warning,
+            route: AlertRoute::DiscordDm,
+            target: Some("1234".into()),
+            dedupe_secs: 600,
+            quiet_hours: Some("23:00-08:00".parse().unwrap()),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(rule.validate().is_ok());
+        assert!(rule.matches(&alert(AlertKind::PlatformDisconnect, AlertSeverity::Warning)));
+        assert!(!rule.matches(&alert(AlertKind::PlatformDisconnect, AlertSeverity::Info)));
+        assert!(!rule.matches(&alert(AlertKind::LowDisk, AlertSeverity::Critical)));
+
+        assert!(rule.is_quiet(&alert(AlertKind::PlatformDisconnect, AlertSeverity::Warning), time("02:00")));
+        assert!(!rule.is_quiet(&alert(AlertKind::PlatformDisconnect, AlertSeverity::Critical), time("02:00")));
+        assert!(!rule.is_quiet(&alert(AlertKind::PlatformDisconnect, AlertSeverity::Warning), time("10:00")));
+
+        let no_target = AlertRule { target: None, ..rule };
+        assert!(no_target.validate().is_err());
+    }
+}
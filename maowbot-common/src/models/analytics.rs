@@ -34,3 +34,103 @@ pub struct BotEvent {
     pub event_timestamp: DateTime<Utc>,
     pub data: Option<Value>,
 }
+
+/// Filters for searching the chat archive. `None` filters match everything.
+#[derive(Clone, Debug, Default)]
+pub struct ChatSearchQuery {
+    pub user_id: Option<Uuid>,
+    pub platform: Option<String>,
+    pub channel: Option<String>,
+    /// Full-text query (websearch syntax: words, "quoted phrases", -excluded)
+    pub text: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// How long messages from one channel are kept (row of `chat_logging_config`).
+#[derive(Clone, Debug, FromRow)]
+pub struct ChatRetentionPolicy {
+    pub platform: String,
+    pub channel: String,
+    pub retention_days: i32,
+    pub is_enabled: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Length of one `chat_activity` bucket.
+pub const CHAT_ACTIVITY_BUCKET_SECONDS: i64 = 300;
+
+/// Messages seen in one channel during one 5-minute bucket (row of `chat_activity`).
+#[derive(Clone, Debug, PartialEq, FromRow)]
+pub struct ChatActivityBucket {
+    pub platform: String,
+    pub channel: String,
+    pub bucket_start: DateTime<Utc>,
+    pub message_count: i32,
+}
+
+/// Start of the bucket `t` falls in.
+pub fn activity_bucket_start(t: DateTime<Utc>) -> DateTime<Utc> {
+    let secs = t.timestamp().div_euclid(CHAT_ACTIVITY_BUCKET_SECONDS) * CHAT_ACTIVITY_BUCKET_SECONDS;
+    DateTime::from_timestamp(secs, 0).unwrap_or(t)
+}
+
+/// Per-bucket counts for a batch of messages, ordered by channel then time.
+pub fn count_activity(messages: &[ChatMessage]) -> Vec<ChatActivityBucket> {
+    let mut counts: std::collections::BTreeMap<(&str, &str, DateTime<Utc>), i32> = Default::default();
+    for msg in messages {
+        *counts.entry((&msg.platform, &msg.channel, activity_bucket_start(msg.timestamp))).or_default() += 1;
+    }
+    counts.into_iter()
+        .map(|((platform, channel, bucket_start), message_count)| ChatActivityBucket {
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+            bucket_start,
+            message_count,
+        })
+        .collect()
+}
+
+/// The `n` busiest buckets, busiest first; ties go to the earlier bucket.
+pub fn peak_buckets(buckets: &[ChatActivityBucket], n: usize) -> Vec<ChatActivityBucket> {
+    let mut sorted: Vec<ChatActivityBucket> = buckets.iter()
+        .filter(|b| b.message_count > 0)
+        .cloned()
+        .collect();
+    sorted.sort_by(|a, b| b.message_count.cmp(&a.message_count).then(a.bucket_start.cmp(&b.bucket_start)));
+    sorted.truncate(n);
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(channel: &str, secs: i64) -> ChatMessage {
+        ChatMessage {
+            message_id: Uuid::new_v4(),
+            platform: "twitch-irc".to_string(),
+            channel: channel.to_string(),
+            user_id: Uuid::nil(),
+            message_text: String::new(),
+            timestamp: DateTime::from_timestamp(secs, 0).unwrap(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_messages_per_five_minutes() {
+        let messages = [msg("#a", 600), msg("#a", 899), msg("#a", 900), msg("#b", 650)];
+        let buckets = count_activity(&messages);
+        let counts: Vec<(&str, i64, i32)> = buckets.iter()
+            .map(|b| (b.channel.as_str(), b.bucket_start.timestamp(), b.message_count))
+            .collect();
+        assert_eq!(counts, vec![("#a", 600, 2), ("#a", 900, 1), ("#b", 600, 1)]);
+
+        let peaks = peak_buckets(&buckets, 2);
+        assert_eq!(peaks[0].message_count, 2);
+        assert_eq!((peaks[1].channel.as_str(), peaks[1].bucket_start.timestamp()), ("#b", 600));
+    }
+}
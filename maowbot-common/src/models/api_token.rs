@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// What a gRPC client may do. Each service method requires one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Permission {
+    /// View data (dashboards)
+    Read,
+    /// Chat moderation and content: commands, redeems, pipelines, scenes
+    Moderate,
+    /// Server control, credentials, config, plugins, tokens
+    Admin,
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Permission::Read => write!(f, "read"),
+            Permission::Moderate => write!(f, "moderate"),
+            Permission::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// The role an API token is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApiRole {
+    Admin,
+    Moderator,
+    ReadOnly,
+}
+
+impl ApiRole {
+    pub fn grants(&self, permission: Permission) -> bool {
+        let highest = match self {
+            ApiRole::Admin => Permission::Admin,
+            ApiRole::Moderator => Permission::Moderate,
+            ApiRole::ReadOnly => Permission::Read,
+        };
+        permission <= highest
+    }
+}
+
+impl fmt::Display for ApiRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiRole::Admin => write!(f, "admin"),
+            ApiRole::Moderator => write!(f, "moderator"),
+            ApiRole::ReadOnly => write!(f, "readonly"),
+        }
+    }
+}
+
+impl FromStr for ApiRole {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "admin" => Ok(ApiRole::Admin),
+            "moderator" | "mod" => Ok(ApiRole::Moderator),
+            "readonly" | "read-only" | "dashboard" => Ok(ApiRole::ReadOnly),
+            other => Err(Error::Parse(format!(
+                "Unknown role '{}' (expected admin, moderator or readonly)", other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub token_id: Uuid,
+    pub name: String,
+    pub role: ApiRole,
+    /// Hex SHA-256 of the token
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// The only workspace the token may use; None for every workspace
+    pub workspace_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    pub audit_id: i64,
+    pub token_id: Option<Uuid>,
+    pub client_name: Option<String>,
+    pub role: Option<String>,
+    /// Full gRPC method path, e.g. "/maowbot.services.ConfigService/SetConfig"
+    pub method: String,
+    pub workspace: Option<String>,
+    pub allowed: bool,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// What a state-changing call changed, e.g. "config:chat.prefix", as noted by the service
+    pub target: Option<String>,
+    pub before_value: Option<serde_json::Value>,
+    pub after_value: Option<serde_json::Value>,
+    /// How a state-changing call ended: "Ok" or a gRPC code such as "NotFound".
+    /// None for denied calls.
+    pub outcome: Option<String>,
+}
+
+/// Narrows `list_audit_entries`. Text filters match case-insensitively anywhere.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    pub method: Option<String>,
+    pub client_name: Option<String>,
+    pub target: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    /// Only calls that needed more than read permission and ran (no denials)
+    pub changes_only: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_grants() {
+        assert!(ApiRole::Admin.grants(Permission::Admin));
+        assert!(ApiRole::Moderator.grants(Permission::Moderate));
+        assert!(ApiRole::Moderator.grants(Permission::Read));
+        assert!(!ApiRole::Moderator.grants(Permission::Admin));
+        assert!(ApiRole::ReadOnly.grants(Permission::Read));
+        assert!(!ApiRole::ReadOnly.grants(Permission::Moderate));
+        assert_eq!("Dashboard".parse::<ApiRole>().unwrap(), ApiRole::ReadOnly);
+        assert!("owner".parse::<ApiRole>().is_err());
+    }
+}
@@ -1,9 +1,7 @@
 use std::sync::Arc;
-use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use tokio::sync::{oneshot, Mutex};
 pub use crate::models::platform::Platform;
-use crate::models::user::User;
 
 #[derive(Debug, Clone)]
 pub enum AuthenticationPrompt {
@@ -12,6 +10,9 @@ pub enum AuthenticationPrompt {
     ApiKey { message: String },
     MultipleKeys { fields: Vec<String>, messages: Vec<String> },
     TwoFactor { message: String },
+    /// OAuth device authorization grant: the user enters `user_code` at
+    /// `verification_uri` on any device, while we poll every `interval` seconds.
+    DeviceCode { user_code: String, verification_uri: String, expires_in: u64, interval: u64 },
     None,
 }
 
@@ -46,9 +47,3 @@ pub struct CallbackServerState {
     /// Once we receive a code, we send it through `done_tx`.
     pub done_tx: Arc<Mutex<Option<oneshot::Sender<CallbackResult>>>>,
 }
-
-#[derive(Debug, Clone)]
-struct CachedUser {
-    user: User,
-    last_access: DateTime<Utc>,
-}
\ No newline at end of file
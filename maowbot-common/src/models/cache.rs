@@ -1,10 +1,14 @@
+use std::collections::HashMap;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Single cached chat message
 #[derive(Debug, Clone)]
 pub struct CachedMessage {
     pub platform: String,
     pub channel: String,
+    pub user_id: Uuid,
     pub user_name: String,
     pub text: String,
     pub timestamp: DateTime<Utc>,
@@ -17,13 +21,36 @@ pub struct CachedMessage {
 pub struct TrimPolicy {
     pub max_age_seconds: Option<i64>,
     pub spam_score_cutoff: Option<f32>,
+    /// Cap across all channels, enforced by the background trim
     pub max_total_messages: Option<usize>,
+    /// Ring size of each channel without its own retention
+    pub max_messages_per_channel: Option<usize>,
     pub max_messages_per_user: Option<usize>,
     pub min_quality_score: Option<f32>,
 }
 
+/// How much history the cache keeps for one channel; unset fields fall back to the `TrimPolicy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelRetention {
+    pub max_messages: Option<usize>,
+    pub max_age_seconds: Option<i64>,
+}
+
 /// Config that the ChatCache will use
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
     pub trim_policy: TrimPolicy,
-}
\ No newline at end of file
+    /// Keyed by `channel_key(platform, channel)`
+    pub channel_retention: HashMap<String, ChannelRetention>,
+}
+
+impl CacheConfig {
+    pub fn new(trim_policy: TrimPolicy) -> Self {
+        Self { trim_policy, channel_retention: HashMap::new() }
+    }
+}
+
+/// Normalized "platform:channel" key; Twitch's leading '#' and case are ignored.
+pub fn channel_key(platform: &str, channel: &str) -> String {
+    format!("{}:{}", platform.to_lowercase(), channel.trim_start_matches('#').to_lowercase())
+}
@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A Twitch clip of the broadcaster that has been posted to Discord.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostedClip {
+    /// Twitch clip slug, e.g. "AwkwardHelplessSalamanderSwiftRage"
+    pub clip_id: String,
+    pub broadcaster_id: String,
+    pub title: String,
+    pub creator_name: String,
+    pub url: String,
+    /// When the clip was made on Twitch
+    pub created_at: DateTime<Utc>,
+    pub posted_at: DateTime<Utc>,
+    /// Chatter who made it with `!clipit`; None for clips found by polling
+    pub requested_by: Option<String>,
+}
@@ -1,9 +1,12 @@
 // File: maowbot-common/src/models/command.rs
 
+use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::Error;
+
 /// Represents a custom or built-in command.
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Command {
@@ -19,6 +22,32 @@ pub struct Command {
     /// (for spam-limiting).
     pub cooldown_seconds: i32,
 
+    /// Seconds before the same user may use the command again,
+    /// independent of the global cooldown.
+    #[serde(default)]
+    pub user_cooldown_seconds: i32,
+
+    /// Cooldowns shared by everyone holding a chat role, keyed by the
+    /// lowercase role name (e.g. "subscriber", "vip"). A chatter holding
+    /// several of these roles waits for all of them.
+    #[serde(default)]
+    #[sqlx(json)]
+    pub role_cooldowns: HashMap<String, i32>,
+
+    /// If true, moderators and the broadcaster skip every cooldown.
+    #[serde(default)]
+    pub cooldown_bypass_mods: bool,
+
+    /// If true, responses are whispered to the user instead of posted in chat
+    /// (Twitch only; other platforms still reply in chat).
+    #[serde(default)]
+    pub reply_privately: bool,
+
+    /// If true, responses are sent as a reply to the message that triggered
+    /// the command (Twitch reply threads, Discord message references).
+    #[serde(default)]
+    pub reply_to_message: bool,
+
     /// If true, user will be warned once that the command is on cooldown
     /// and cannot be used again, but will not spam subsequent warnings
     /// for repeated triggers by the same user in the cooldown period.
@@ -29,6 +58,11 @@ pub struct Command {
     /// so we can choose “bot” or “broadcaster” or a “team” account.
     pub respond_with_credential: Option<Uuid>,
 
+    /// Text the command answers with when it has no built-in logic;
+    /// `{user}`, `{touser}`, `{channel}` and `{args}` are filled in.
+    #[serde(default)]
+    pub response: Option<String>,
+
     /// If true, the command only works when the stream is live.
     pub stream_online_only: bool,
 
@@ -48,8 +82,173 @@ pub struct CommandUsage {
     pub usage_id: Uuid,
     pub command_id: Uuid,
     pub user_id: Uuid,
+    #[serde(default)]
+    pub platform: String,
     pub used_at: DateTime<Utc>,
     pub channel: String,
     pub usage_text: String,
     pub metadata: Option<serde_json::Value>,
+    /// How long the command's handler took; None for uses logged before timing
+    #[serde(default)]
+    pub duration_ms: Option<i32>,
+    /// Set when the handler failed
+    #[serde(default)]
+    pub error_message: Option<String>,
+}
+
+/// How one command has been doing over a period: how often it ran, how often
+/// its handler failed and how long the handler took.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommandStats {
+    pub uses: i64,
+    pub errors: i64,
+    /// Uses with a recorded handler time; the latencies cover only these
+    pub timed_uses: i64,
+    pub avg_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl CommandStats {
+    /// Share of uses that failed, 0.0 to 1.0.
+    pub fn error_rate(&self) -> f64 {
+        if self.uses == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.uses as f64
+        }
+    }
+
+    /// The stats of `command_id` from its uses at or after `since`, computed
+    /// the way the Postgres query does (percentiles interpolate like
+    /// `percentile_cont`), for backends that can't aggregate themselves.
+    pub fn from_usages(usages: &[CommandUsage], command_id: Uuid, since: DateTime<Utc>) -> Self {
+        let mut window: Vec<&CommandUsage> = usages.iter()
+            .filter(|u| u.command_id == command_id && u.used_at >= since)
+            .collect();
+        window.sort_by_key(|u| u.used_at);
+
+        let mut timings: Vec<f64> = window.iter().filter_map(|u| u.duration_ms).map(f64::from).collect();
+        timings.sort_by(|a, b| a.total_cmp(b));
+        let avg_ms = if timings.is_empty() { 0.0 } else { timings.iter().sum::<f64>() / timings.len() as f64 };
+
+        Self {
+            uses: window.len() as i64,
+            errors: window.iter().filter(|u| u.error_message.is_some()).count() as i64,
+            timed_uses: timings.len() as i64,
+            avg_ms,
+            p50_ms: percentile_cont(&timings, 0.5),
+            p95_ms: percentile_cont(&timings, 0.95),
+            p99_ms: percentile_cont(&timings, 0.99),
+            max_ms: timings.last().copied().unwrap_or(0.0),
+            last_used_at: window.last().map(|u| u.used_at),
+            last_error: window.iter().rev().find_map(|u| u.error_message.clone()),
+        }
+    }
+}
+
+/// Linear interpolation between the closest ranks of sorted `values`; 0 when empty.
+fn percentile_cont(sorted: &[f64], fraction: f64) -> f64 {
+    let Some(last) = sorted.len().checked_sub(1) else { return 0.0 };
+    let position = fraction * last as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Formats role cooldowns as "role=secs,role=secs", sorted by role.
+pub fn format_role_cooldowns(cooldowns: &HashMap<String, i32>) -> String {
+    let mut pairs: Vec<_> = cooldowns.iter().collect();
+    pairs.sort();
+    pairs.iter()
+        .map(|(role, secs)| format!("{}={}", role, secs))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses "role=secs,role=secs" as written by [`format_role_cooldowns`].
+/// Roles are lowercased; a cooldown of 0 drops the role.
+pub fn parse_role_cooldowns(s: &str) -> Result<HashMap<String, i32>, Error> {
+    let mut cooldowns = HashMap::new();
+    for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (role, secs) = pair.split_once('=')
+            .ok_or_else(|| Error::Parse(format!("Expected role=seconds, got '{}'", pair)))?;
+        let secs: i32 = secs.trim().parse().ok().filter(|s: &i32| *s >= 0)
+            .ok_or_else(|| Error::Parse(format!("Invalid cooldown '{}' for role '{}'", secs.trim(), role.trim())))?;
+        if secs > 0 {
+            cooldowns.insert(role.trim().to_lowercase(), secs);
+        }
+    }
+    Ok(cooldowns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn usage(command_id: Uuid, minutes_ago: i64, duration_ms: Option<i32>, error: Option<&str>) -> CommandUsage {
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        CommandUsage {
+            usage_id: Uuid::new_v4(),
+            command_id,
+            user_id: Uuid::new_v4(),
+            platform: "twitch-irc".to_string(),
+            used_at: now - Duration::minutes(minutes_ago),
+            channel: "#maowcaster".to_string(),
+            usage_text: String::new(),
+            metadata: None,
+            duration_ms,
+            error_message: error.map(str::to_string),
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_stats_cover_one_command_inside_the_window() {
+        let (ping, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let usages = vec![
+            usage(ping, 50, Some(10), None),
+            usage(ping, 40, Some(40), Some("timeout")),
+            usage(ping, 30, Some(30), None),
+            usage(ping, 20, None, Some("no permission")),
+            usage(ping, 10, Some(20), None),
+            // Before the window
+            usage(ping, 120, Some(5000), Some("old")),
+            // Another command
+            usage(other, 5, Some(900), Some("other")),
+        ];
+        let since = Utc.with_ymd_and_hms(2026, 10, 16, 11, 0, 0).unwrap();
+        let stats = CommandStats::from_usages(&usages, ping, since);
+
+        assert_eq!((stats.uses, stats.errors, stats.timed_uses), (5, 2, 4));
+        assert!(close(stats.error_rate(), 0.4));
+        assert!(close(stats.avg_ms, 25.0));
+        assert!(close(stats.p50_ms, 25.0));
+        assert!(close(stats.p95_ms, 38.5));
+        assert!(close(stats.p99_ms, 39.7));
+        assert!(close(stats.max_ms, 40.0));
+        assert_eq!(stats.last_used_at, Some(since + Duration::minutes(50)));
+        assert_eq!(stats.last_error.as_deref(), Some("no permission"));
+    }
+
+    #[test]
+    fn test_stats_without_uses_or_timings_are_zero() {
+        let id = Uuid::new_v4();
+        let empty = CommandStats::from_usages(&[], id, Utc::now());
+        assert_eq!(empty.uses, 0);
+        assert!(close(empty.p99_ms, 0.0));
+        assert!(empty.last_used_at.is_none());
+
+        let untimed = CommandStats::from_usages(&[usage(id, 1, None, None)], id, Utc.timestamp_opt(0, 0).unwrap());
+        assert_eq!((untimed.uses, untimed.timed_uses), (1, 0));
+        assert!(close(untimed.avg_ms, 0.0));
+        assert!(close(percentile_cont(&[7.0], 0.95), 7.0));
+    }
 }
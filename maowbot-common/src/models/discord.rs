@@ -132,6 +132,12 @@ pub struct DiscordEmbed {
     pub fields: Vec<DiscordEmbedField>,
 }
 
+impl Default for DiscordEmbed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DiscordEmbed {
     pub fn new() -> Self {
         Self {
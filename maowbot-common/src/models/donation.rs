@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Tip service a donation came in through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DonationSource {
+    Streamlabs,
+    StreamElements,
+}
+
+impl fmt::Display for DonationSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DonationSource::Streamlabs => write!(f, "streamlabs"),
+            DonationSource::StreamElements => write!(f, "streamelements"),
+        }
+    }
+}
+
+impl FromStr for DonationSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "streamlabs" => Ok(DonationSource::Streamlabs),
+            "streamelements" | "se" => Ok(DonationSource::StreamElements),
+            other => Err(Error::Parse(format!(
+                "Unknown donation source '{}' (expected streamlabs or streamelements)", other
+            ))),
+        }
+    }
+}
+
+/// A tip from Streamlabs or StreamElements, normalized to one shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Donation {
+    pub donation_id: Uuid,
+    pub source: DonationSource,
+    /// The service's own id, used to drop replays after a reconnect
+    pub external_id: Option<String>,
+    pub donor_name: String,
+    pub message: Option<String>,
+    /// Amount in `currency`, rounded to that currency's minor unit
+    pub amount: f64,
+    /// ISO 4217 code, upper case
+    pub currency: String,
+    /// `amount` converted to `base_currency`; None if no rate is configured
+    pub amount_base: Option<f64>,
+    pub base_currency: String,
+    pub received_at: DateTime<Utc>,
+}
+
+impl Donation {
+    pub fn new(source: DonationSource, donor_name: &str, amount: f64, currency: Option<&str>) -> Self {
+        let currency = currency.unwrap_or_default().to_string();
+        Self {
+            donation_id: Uuid::new_v4(),
+            source,
+            external_id: None,
+            donor_name: donor_name.trim().to_string(),
+            message: None,
+            amount,
+            base_currency: currency.clone(),
+            currency,
+            amount_base: None,
+            received_at: Utc::now(),
+        }
+    }
+
+    pub fn with_external_id(mut self, id: Option<String>) -> Self {
+        self.external_id = id.filter(|i| !i.is_empty());
+        self
+    }
+
+    pub fn with_message(mut self, message: Option<String>) -> Self {
+        self.message = message.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+        self
+    }
+
+    /// Rounds the amount and fills in `amount_base`. An unusable currency
+    /// code is taken to be `base_currency`.
+    pub fn normalize(mut self, base_currency: &str, rates: &HashMap<String, f64>) -> Self {
+        self.base_currency = normalize_currency(Some(base_currency), "USD");
+        self.currency = normalize_currency(Some(&self.currency), &self.base_currency);
+        self.amount = round_to_minor_unit(self.amount, &self.currency);
+        self.amount_base = convert_amount(self.amount, &self.currency, &self.base_currency, rates)
+            .map(|a| round_to_minor_unit(a, &self.base_currency));
+        self
+    }
+
+    /// "5.00 USD", or "500 JPY" for zero-decimal currencies.
+    pub fn formatted_amount(&self) -> String {
+        format!("{:.*} {}", minor_unit_digits(&self.currency), self.amount, self.currency)
+    }
+}
+
+/// Total given by one donor, in the base currency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonorTotal {
+    pub donor_name: String,
+    pub total: f64,
+    pub donation_count: i64,
+}
+
+/// Currencies whose amounts carry no decimals.
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "VND", "VUV", "XAF", "XOF", "XPF",
+];
+
+pub fn minor_unit_digits(currency: &str) -> usize {
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency) { 0 } else { 2 }
+}
+
+pub fn round_to_minor_unit(amount: f64, currency: &str) -> f64 {
+    let factor = 10f64.powi(minor_unit_digits(currency) as i32);
+    (amount * factor).round() / factor
+}
+
+/// Upper-cased three-letter code, or `fallback` when missing or malformed.
+pub fn normalize_currency(code: Option<&str>, fallback: &str) -> String {
+    match code.map(str::trim) {
+        Some(c) if c.len() == 3 && c.chars().all(|ch| ch.is_ascii_alphabetic()) => c.to_ascii_uppercase(),
+        _ => fallback.to_ascii_uppercase(),
+    }
+}
+
+/// Parses amounts as the tip services send them: numbers, or strings such as
+/// "5", "5.00", "$5.00" or "5,50".
+pub fn parse_amount(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => {
+            let cleaned: String = s.chars()
+                .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',' || *c == '-')
+                .collect();
+            // A lone comma not followed by three digits is a decimal separator ("5,50");
+            // otherwise commas group thousands ("1,234.50")
+            let decimal_comma = !cleaned.contains('.')
+                && cleaned.matches(',').count() == 1
+                && cleaned.split(',').nth(1).is_some_and(|d| d.len() != 3);
+            let cleaned = if decimal_comma {
+                cleaned.replace(',', ".")
+            } else {
+                cleaned.replace(',', "")
+            };
+            cleaned.parse().ok()
+        }
+        _ => None,
+    }
+    .filter(|a: &f64| a.is_finite() && *a >= 0.0)
+}
+
+/// Converts with `rates`, which map a currency code to how many units of the
+/// base currency one unit of it is worth.
+pub fn convert_amount(amount: f64, from: &str, base: &str, rates: &HashMap<String, f64>) -> Option<f64> {
+    if from.eq_ignore_ascii_case(base) {
+        return Some(amount);
+    }
+    rates.iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(from))
+        .map(|(_, rate)| amount * rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalizes_amounts_and_currencies() {
+        assert_eq!(parse_amount(&json!(5)), Some(5.0));
+        assert_eq!(parse_amount(&json!("$5.00")), Some(5.0));
+        assert_eq!(parse_amount(&json!("5,50")), Some(5.5));
+        assert_eq!(parse_amount(&json!("1,234.50")), Some(1234.5));
+        assert_eq!(parse_amount(&json!("1,234")), Some(1234.0));
+        assert_eq!(parse_amount(&json!("abc")), None);
+
+        let rates = HashMap::from([("EUR".to_string(), 1.1)]);
+        let d = Donation::new(DonationSource::Streamlabs, "foo", 10.006, Some("eur")).normalize("usd", &rates);
+        assert_eq!(d.currency, "EUR");
+        assert_eq!(d.base_currency, "USD");
+        assert_eq!(d.amount, 10.01);
+        assert_eq!(d.amount_base, Some(11.01));
+
+        let d = Donation::new(DonationSource::StreamElements, "bar", 500.4, Some("JPY")).normalize("USD", &rates);
+        assert_eq!(d.formatted_amount(), "500 JPY");
+        assert_eq!(d.amount_base, None);
+    }
+}
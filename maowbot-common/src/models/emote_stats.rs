@@ -0,0 +1,71 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Where an emote comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum EmoteProvider {
+    Twitch,
+    SevenTv,
+    Bttv,
+    Ffz,
+}
+
+impl fmt::Display for EmoteProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmoteProvider::Twitch => write!(f, "twitch"),
+            EmoteProvider::SevenTv => write!(f, "7tv"),
+            EmoteProvider::Bttv => write!(f, "bttv"),
+            EmoteProvider::Ffz => write!(f, "ffz"),
+        }
+    }
+}
+
+impl FromStr for EmoteProvider {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "twitch" => Ok(EmoteProvider::Twitch),
+            "7tv" | "seventv" => Ok(EmoteProvider::SevenTv),
+            "bttv" => Ok(EmoteProvider::Bttv),
+            "ffz" => Ok(EmoteProvider::Ffz),
+            other => Err(Error::Parse(format!("Unknown emote provider '{}'", other))),
+        }
+    }
+}
+
+impl EmoteProvider {
+    /// The emote's smallest image on the provider's CDN.
+    pub fn image_url(&self, emote_id: &str) -> String {
+        match self {
+            EmoteProvider::Twitch => format!("https://static-cdn.jtvnw.net/emoticons/v2/{}/default/dark/1.0", emote_id),
+            EmoteProvider::SevenTv => format!("https://cdn.7tv.app/emote/{}/1x.webp", emote_id),
+            EmoteProvider::Bttv => format!("https://cdn.betterttv.net/emote/{}/1x", emote_id),
+            EmoteProvider::Ffz => format!("https://cdn.frankerfacez.com/emote/{}/1", emote_id),
+        }
+    }
+}
+
+/// How often one emote was used in a channel over some period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmoteUsage {
+    /// Lowercase channel login, without '#'
+    pub channel: String,
+    pub provider: EmoteProvider,
+    pub emote_id: String,
+    /// The code typed in chat
+    pub emote_name: String,
+    pub uses: i64,
+}
+
+/// Uses of one emote in a channel on one (UTC) day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmoteDailyCount {
+    pub day: NaiveDate,
+    pub usage: EmoteUsage,
+}
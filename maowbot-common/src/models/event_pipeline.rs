@@ -113,6 +113,54 @@ pub struct PipelineSharedData {
     pub created_at: DateTime<Utc>,
 }
 
+/// Rate limits for one pipeline. Zero turns a limit off. Executions are
+/// counted in `pipeline_throttle_hits`, so restarts don't reset them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineThrottle {
+    pub pipeline_id: Uuid,
+    /// Seconds between two runs of the pipeline
+    pub cooldown_seconds: i32,
+    /// Seconds between two runs for the same user
+    pub user_cooldown_seconds: i32,
+    /// Runs allowed per `window_seconds`
+    pub max_executions: i32,
+    /// Runs allowed per user per `window_seconds`
+    pub max_executions_per_user: i32,
+    pub window_seconds: i32,
+}
+
+impl PipelineThrottle {
+    /// Longest a limit may look back (a week).
+    pub const MAX_SECONDS: i32 = 7 * 24 * 3600;
+
+    pub fn is_active(&self) -> bool {
+        self.cooldown_seconds > 0
+            || self.user_cooldown_seconds > 0
+            || (self.max_executions > 0 && self.window_seconds > 0)
+            || (self.max_executions_per_user > 0 && self.window_seconds > 0)
+    }
+
+    /// How far back the pipeline-wide limits look, in seconds.
+    pub fn pipeline_horizon(&self) -> i32 {
+        let window = if self.max_executions > 0 { self.window_seconds } else { 0 };
+        self.cooldown_seconds.max(window)
+    }
+
+    /// How far back the per-user limits look, in seconds.
+    pub fn user_horizon(&self) -> i32 {
+        let window = if self.max_executions_per_user > 0 { self.window_seconds } else { 0 };
+        self.user_cooldown_seconds.max(window)
+    }
+}
+
+/// Recent runs of a pipeline in one throttle scope (the whole pipeline or one user).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThrottleUsage {
+    /// Runs inside the rate window
+    pub in_window: i64,
+    pub last_hit: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventTypeRegistry {
     pub event_type_id: Uuid,
@@ -232,7 +280,7 @@ impl EventPipeline {
 }
 
 impl PipelineFilter {
-    pub fn applies_to_event(&self, event_type: &str, event_data: &serde_json::Value) -> bool {
+    pub fn applies_to_event(&self, _event_type: &str, _event_data: &serde_json::Value) -> bool {
         // This would be implemented based on the filter type
         // For now, return true as placeholder
         true
@@ -244,8 +292,8 @@ impl PipelineAction {
         match &self.condition_type {
             None => true,
             Some(condition) => match condition.as_str() {
-                "previous_success" => previous_result.map_or(true, |r| r.status == ActionExecutionStatus::Success),
-                "previous_failure" => previous_result.map_or(false, |r| r.status == ActionExecutionStatus::Failed),
+                "previous_success" => previous_result.is_none_or(|r| r.status == ActionExecutionStatus::Success),
+                "previous_failure" => previous_result.is_some_and(|r| r.status == ActionExecutionStatus::Failed),
                 _ => true,
             }
         }
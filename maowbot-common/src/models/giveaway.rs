@@ -0,0 +1,157 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GiveawayStatus {
+    /// Accepting entries
+    Open,
+    /// Entries closed, winners may still be drawn or re-rolled
+    Closed,
+    Cancelled,
+}
+
+impl fmt::Display for GiveawayStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GiveawayStatus::Open => write!(f, "open"),
+            GiveawayStatus::Closed => write!(f, "closed"),
+            GiveawayStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl FromStr for GiveawayStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(GiveawayStatus::Open),
+            "closed" => Ok(GiveawayStatus::Closed),
+            "cancelled" => Ok(GiveawayStatus::Cancelled),
+            other => Err(Error::Parse(format!("Unknown giveaway status '{}'", other))),
+        }
+    }
+}
+
+/// A giveaway on one Twitch channel. Viewers enter by typing `keyword` in
+/// chat, or by redeeming the channel point reward created for `points_cost`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Giveaway {
+    pub giveaway_id: Uuid,
+    pub title: String,
+    pub channel: String,
+    pub keyword: Option<String>,
+    /// Channel points to enter, 0 when entry is by keyword only
+    pub points_cost: i64,
+    /// The custom reward created for `points_cost`
+    pub reward_id: Option<String>,
+    pub rules: GiveawayRules,
+    pub status: GiveawayStatus,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// Who may enter, and how heavily each entry counts in the draw.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GiveawayRules {
+    pub sub_only: bool,
+    /// Minimum days following the channel, 0 to allow non-followers
+    pub min_follow_days: i32,
+    /// Users who won any giveaway in this many days can't enter, 0 to allow them
+    pub exclude_winners_days: i32,
+    /// Draw weight of a subscriber's entry; everyone else counts once
+    pub sub_weight: i32,
+}
+
+impl Default for GiveawayRules {
+    fn default() -> Self {
+        Self {
+            sub_only: false,
+            min_follow_days: 0,
+            exclude_winners_days: 0,
+            sub_weight: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GiveawayEntry {
+    pub entry_id: Uuid,
+    pub giveaway_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub is_subscriber: bool,
+    pub weight: i32,
+    pub is_winner: bool,
+    /// A winner that was re-rolled; never drawn again
+    pub rerolled: bool,
+    pub entered_at: DateTime<Utc>,
+    pub won_at: Option<DateTime<Utc>>,
+}
+
+impl GiveawayEntry {
+    /// Whether the entry can still be drawn.
+    pub fn in_draw(&self) -> bool {
+        !self.is_winner && !self.rerolled
+    }
+}
+
+/// Picks the entry that `roll` lands on, where `roll` is uniform in
+/// `0..total_weight(entries)`. Winners and re-rolled entries are skipped.
+pub fn pick_weighted(entries: &[GiveawayEntry], roll: u64) -> Option<&GiveawayEntry> {
+    let mut remaining = roll;
+    for entry in entries.iter().filter(|e| e.in_draw()) {
+        let weight = entry.weight.max(1) as u64;
+        if remaining < weight {
+            return Some(entry);
+        }
+        remaining -= weight;
+    }
+    None
+}
+
+/// Sum of the weights of every entry still in the draw.
+pub fn total_weight(entries: &[GiveawayEntry]) -> u64 {
+    entries.iter().filter(|e| e.in_draw()).map(|e| e.weight.max(1) as u64).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(username: &str, weight: i32) -> GiveawayEntry {
+        GiveawayEntry {
+            entry_id: Uuid::new_v4(),
+            giveaway_id: Uuid::nil(),
+            user_id: Uuid::new_v4(),
+            username: username.into(),
+            is_subscriber: weight > 1,
+            weight,
+            is_winner: false,
+            rerolled: false,
+            entered_at: Utc::now(),
+            won_at: None,
+        }
+    }
+
+    #[test]
+    fn test_picks_by_weight_and_skips_winners() {
+        let mut entries = vec![entry("a", 1), entry("b", 3), entry("c", 1)];
+        assert_eq!(total_weight(&entries), 5);
+        assert_eq!(pick_weighted(&entries, 0).unwrap().username, "a");
+        assert_eq!(pick_weighted(&entries, 1).unwrap().username, "b");
+        assert_eq!(pick_weighted(&entries, 3).unwrap().username, "b");
+        assert_eq!(pick_weighted(&entries, 4).unwrap().username, "c");
+        assert!(pick_weighted(&entries, 5).is_none());
+
+        entries[1].is_winner = true;
+        entries[2].rerolled = true;
+        assert_eq!(total_weight(&entries), 1);
+        assert_eq!(pick_weighted(&entries, 0).unwrap().username, "a");
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A response template stored in the database, overriding or extending the
+/// bundled language packs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageString {
+    /// Lowercase language tag, e.g. "en", "pt-br"
+    pub language: String,
+    /// Message key, e.g. "command.cooldown"
+    pub key: String,
+    /// Text with `{name}` placeholders
+    pub template: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The language bot responses use in one channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelLanguage {
+    /// e.g. "twitch-irc"
+    pub platform: String,
+    /// Lowercase, without '#'
+    pub channel: String,
+    pub language: String,
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// The kind of MIDI message a mapping listens for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MidiTriggerKind {
+    /// A key or pad; pressed with Note On, released with Note Off
+    Note,
+    /// A knob, fader or button sending Control Change values 0–127
+    ControlChange,
+}
+
+impl fmt::Display for MidiTriggerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiTriggerKind::Note => write!(f, "note"),
+            MidiTriggerKind::ControlChange => write!(f, "cc"),
+        }
+    }
+}
+
+impl FromStr for MidiTriggerKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "note" => Ok(MidiTriggerKind::Note),
+            "cc" | "control" | "control_change" => Ok(MidiTriggerKind::ControlChange),
+            other => Err(Error::Parse(format!("Unknown MIDI trigger '{}' (expected note or cc)", other))),
+        }
+    }
+}
+
+/// One note or controller on one channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MidiTrigger {
+    pub kind: MidiTriggerKind,
+    /// 1–16, as controllers label them
+    pub channel: u8,
+    /// Note or controller number, 0–127
+    pub number: u8,
+}
+
+impl fmt::Display for MidiTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ch{}", self.kind, self.number, self.channel)
+    }
+}
+
+/// What a mapped note or controller does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MidiActionKind {
+    /// Sets the avatar parameter in `target`; `argument` is bool, int or float
+    Osc,
+    /// Switches OBS to the scene in `target`; `argument` is the OBS instance
+    ObsScene,
+    /// Sends `target` to Twitch chat; `argument` is the channel, blank for the broadcaster's
+    Chat,
+}
+
+impl fmt::Display for MidiActionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiActionKind::Osc => write!(f, "osc"),
+            MidiActionKind::ObsScene => write!(f, "obs_scene"),
+            MidiActionKind::Chat => write!(f, "chat"),
+        }
+    }
+}
+
+impl FromStr for MidiActionKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "osc" => Ok(MidiActionKind::Osc),
+            "obs_scene" | "scene" | "obs" => Ok(MidiActionKind::ObsScene),
+            "chat" => Ok(MidiActionKind::Chat),
+            other => Err(Error::Parse(format!("Unknown MIDI action '{}' (expected osc, scene or chat)", other))),
+        }
+    }
+}
+
+/// A MIDI note or controller bound to a bot action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MidiMapping {
+    pub mapping_id: Uuid,
+    /// Unique
+    pub name: String,
+    pub trigger: MidiTrigger,
+    pub action: MidiActionKind,
+    /// Parameter name, scene name or chat text
+    pub target: String,
+    /// See `MidiActionKind`
+    pub argument: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// What a viewer reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MilestoneKind {
+    /// Every 12 months subscribed, counted in years
+    SubAnniversary,
+    /// A sub streak of one of `milestones.streak_months`, counted in months
+    SubStreak,
+    /// A year since following, counted in years
+    FollowAnniversary,
+}
+
+impl MilestoneKind {
+    /// What `value` counts.
+    pub fn unit(self) -> &'static str {
+        match self {
+            MilestoneKind::SubStreak => "months",
+            MilestoneKind::SubAnniversary | MilestoneKind::FollowAnniversary => "years",
+        }
+    }
+}
+
+impl fmt::Display for MilestoneKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MilestoneKind::SubAnniversary => write!(f, "sub_anniversary"),
+            MilestoneKind::SubStreak => write!(f, "sub_streak"),
+            MilestoneKind::FollowAnniversary => write!(f, "follow_anniversary"),
+        }
+    }
+}
+
+impl FromStr for MilestoneKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sub_anniversary" => Ok(MilestoneKind::SubAnniversary),
+            "sub_streak" | "streak" => Ok(MilestoneKind::SubStreak),
+            "follow_anniversary" => Ok(MilestoneKind::FollowAnniversary),
+            other => Err(Error::Parse(format!(
+                "Unknown milestone '{}', expected sub_anniversary, sub_streak or follow_anniversary", other
+            ))),
+        }
+    }
+}
+
+/// A milestone that was celebrated; each one is celebrated once per viewer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewerMilestone {
+    pub milestone_id: Uuid,
+    /// Lowercase channel login, without '#'
+    pub channel: String,
+    pub twitch_user_id: String,
+    pub user_login: String,
+    pub display_name: String,
+    pub kind: MilestoneKind,
+    /// Years or months, see `MilestoneKind::unit`
+    pub value: i32,
+    pub celebrated_at: DateTime<Utc>,
+}
+
+/// The last resub a viewer shared in a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubHistory {
+    pub channel: String,
+    pub twitch_user_id: String,
+    pub cumulative_months: i32,
+    /// None when the viewer didn't share their streak
+    pub streak_months: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -17,10 +17,50 @@ pub mod link_request;
 pub mod discord;
 pub mod ai;
 pub mod event_pipeline;
+pub mod workspace;
+pub mod api_token;
+pub mod user_notes;
+pub mod settings;
+pub mod donation;
+pub mod stream_marker;
+pub mod giveaway;
+pub mod protection;
+pub mod moderation_rule;
+pub mod emote_stats;
+pub mod clip;
+pub mod localization;
+pub mod redeem_schedule;
+pub mod midi_mapping;
+pub mod watchtime;
+pub mod responder;
+pub mod redeem_approval;
+pub mod alert_rule;
+pub mod milestone;
+pub mod stream_session;
 
 pub use user_analysis::UserAnalysis;
-pub use command::{Command, CommandUsage};
+pub use command::{Command, CommandStats, CommandUsage};
 pub use redeem::{Redeem, RedeemUsage};
+pub use workspace::{Workspace, WorkspaceChannel, DEFAULT_WORKSPACE_ID};
+pub use api_token::{ApiRole, ApiToken, AuditLogEntry, AuditLogFilter, Permission};
+pub use user_notes::{ModerationAction, ModerationActionType, UserNote};
+pub use settings::{SettingDefinition, SettingType};
+pub use donation::{Donation, DonationSource, DonorTotal};
+pub use stream_marker::{MarkerSource, StreamMarker, VodChapters};
+pub use giveaway::{Giveaway, GiveawayEntry, GiveawayRules, GiveawayStatus};
+pub use protection::{CapturedMessage, IncidentTrigger, ProtectionIncident};
+pub use moderation_rule::{ModerationRule, RuleKind, RuleSeverity};
+pub use emote_stats::{EmoteDailyCount, EmoteProvider, EmoteUsage};
+pub use clip::PostedClip;
+pub use localization::{ChannelLanguage, LanguageString};
+pub use redeem_schedule::{RedeemSchedule, ScheduleAction, ScheduleCondition};
+pub use midi_mapping::{MidiActionKind, MidiMapping, MidiTrigger, MidiTriggerKind};
+pub use watchtime::ViewerWatchtime;
+pub use responder::{Responder, ResponderMatch};
+pub use redeem_approval::{ApprovalStatus, RedeemApproval};
+pub use alert_rule::{AlertKind, AlertRoute, AlertRule, AlertSeverity, OpsAlert, QuietHours};
+pub use milestone::{MilestoneKind, SubHistory, ViewerMilestone};
+pub use stream_session::{SessionChatter, SessionScope, SessionStats, StreamSession};
 pub use drip::{DripAvatar, DripFit, DripFitParam, DripProp};
 pub use event_pipeline::{
     EventPipeline, PipelineFilter, PipelineAction, PipelineExecutionLog,
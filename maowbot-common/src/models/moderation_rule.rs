@@ -0,0 +1,99 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// What a moderation rule looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RuleKind {
+    /// Any link whose domain isn't allowlisted in the pattern
+    Link,
+    /// One or more `|`-separated phrases, matched case-insensitively as whole words
+    Phrase,
+    /// A regular expression, case-insensitive
+    Regex,
+}
+
+impl fmt::Display for RuleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleKind::Link => write!(f, "link"),
+            RuleKind::Phrase => write!(f, "phrase"),
+            RuleKind::Regex => write!(f, "regex"),
+        }
+    }
+}
+
+impl FromStr for RuleKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "link" | "links" | "url" => Ok(RuleKind::Link),
+            "phrase" | "word" => Ok(RuleKind::Phrase),
+            "regex" => Ok(RuleKind::Regex),
+            other => Err(Error::Parse(format!("Unknown rule kind '{}'", other))),
+        }
+    }
+}
+
+/// What happens to a matching message, from mildest to harshest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RuleSeverity {
+    Delete,
+    Timeout,
+    Ban,
+}
+
+impl fmt::Display for RuleSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSeverity::Delete => write!(f, "delete"),
+            RuleSeverity::Timeout => write!(f, "timeout"),
+            RuleSeverity::Ban => write!(f, "ban"),
+        }
+    }
+}
+
+impl FromStr for RuleSeverity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "delete" => Ok(RuleSeverity::Delete),
+            "timeout" => Ok(RuleSeverity::Timeout),
+            "ban" => Ok(RuleSeverity::Ban),
+            other => Err(Error::Parse(format!("Unknown severity '{}'", other))),
+        }
+    }
+}
+
+/// A chat moderation rule with its hit counter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationRule {
+    pub rule_id: Uuid,
+    /// Unique, shown to the offender as the reason
+    pub name: String,
+    pub kind: RuleKind,
+    /// Phrases, regex, or allowlisted domains (comma or space separated) for links
+    pub pattern: String,
+    pub severity: RuleSeverity,
+    /// Only used by `Timeout`
+    pub timeout_secs: i32,
+    /// Subscribers may post what this rule blocks
+    pub permit_subs: bool,
+    pub permit_vips: bool,
+    pub permit_mods: bool,
+    /// Rules sharing a category share an escalation ladder: repeat offenses
+    /// get `moderation.escalation_ladder`'s next step instead of `severity`.
+    /// Empty for rules that always apply their own severity.
+    pub category: String,
+    pub enabled: bool,
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -67,4 +67,210 @@ impl OscParameterValue {
             _ => Err(format!("Unknown parameter type: {}", value_type)),
         }
     }
-}
\ No newline at end of file
+}
+impl std::fmt::Display for OscParameterValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(v) => write!(f, "{}", if *v { "on" } else { "off" }),
+            Self::Int(v) => write!(f, "{}", v),
+            Self::Float(v) => write!(f, "{:.2}", v),
+        }
+    }
+}
+
+/// Chat roles an `OscChatControl` can require, lowest first.
+pub const OSC_CHAT_ROLES: &[&str] = &["everyone", "subscriber", "vip", "moderator", "broadcaster"];
+
+/// Where a chat role sits in `OSC_CHAT_ROLES`, accepting the badge spellings
+/// the platforms use.
+fn chat_role_rank(role: &str) -> Option<usize> {
+    match role.to_lowercase().as_str() {
+        "everyone" | "viewer" => Some(0),
+        "subscriber" | "sub" | "founder" => Some(1),
+        "vip" => Some(2),
+        "moderator" | "mod" => Some(3),
+        "broadcaster" => Some(4),
+        _ => None,
+    }
+}
+
+/// An avatar parameter chat may drive with `!toggle <name>` (bools) or
+/// `!set <name> <value>`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OscChatControl {
+    pub id: i32,
+    /// What chat types after the command, lowercase
+    pub name: String,
+    pub parameter_name: String,
+    /// "bool", "int" or "float"
+    pub parameter_type: String,
+    /// One of `OSC_CHAT_ROLES`
+    pub min_role: String,
+    /// Values from chat are clamped into these; unset bounds fall back to
+    /// VRChat's own ranges (0..255 for ints, -1..1 for floats)
+    pub min_value: Option<f32>,
+    pub max_value: Option<f32>,
+    pub cooldown_seconds: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OscChatControl {
+    /// Checks a control before it's saved.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() || self.name.chars().any(char::is_whitespace) {
+            return Err("Control names must be a single word".to_string());
+        }
+        if self.parameter_name.trim().is_empty() {
+            return Err("An avatar parameter is required".to_string());
+        }
+        if !matches!(self.parameter_type.as_str(), "bool" | "int" | "float") {
+            return Err(format!("Unknown parameter type: {}", self.parameter_type));
+        }
+        if chat_role_rank(&self.min_role).is_none() {
+            return Err(format!("Unknown role '{}', expected one of {}", self.min_role, OSC_CHAT_ROLES.join(", ")));
+        }
+        if let (Some(min), Some(max)) = (self.min_value, self.max_value) {
+            if min > max {
+                return Err(format!("Minimum {} is above maximum {}", min, max));
+            }
+        }
+        if self.cooldown_seconds < 0 {
+            return Err("Cooldown can't be negative".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether someone with these chat roles may use the control.
+    pub fn allows(&self, roles: &[String]) -> bool {
+        // An unknown requirement only lets the broadcaster through
+        let needed = chat_role_rank(&self.min_role).unwrap_or(OSC_CHAT_ROLES.len() - 1);
+        needed == 0 || roles.iter().filter_map(|r| chat_role_rank(r)).any(|rank| rank >= needed)
+    }
+
+    /// Parses what chat typed for this parameter and clamps it into range.
+    pub fn value_from_chat(&self, raw: &str) -> Result<OscParameterValue, String> {
+        let raw = raw.trim();
+        match self.parameter_type.as_str() {
+            "bool" => match raw.to_lowercase().as_str() {
+                "on" | "true" | "yes" | "1" => Ok(OscParameterValue::Bool(true)),
+                "off" | "false" | "no" | "0" => Ok(OscParameterValue::Bool(false)),
+                _ => Err(format!("'{}' isn't on or off", raw)),
+            },
+            "int" => {
+                let v = parse_chat_number(raw)?;
+                Ok(OscParameterValue::Int(self.clamp(v.round(), 0.0, 255.0) as i32))
+            }
+            "float" => {
+                let v = parse_chat_number(raw)?;
+                Ok(OscParameterValue::Float(self.clamp(v, -1.0, 1.0)))
+            }
+            other => Err(format!("Unknown parameter type: {}", other)),
+        }
+    }
+
+    fn clamp(&self, value: f32, default_min: f32, default_max: f32) -> f32 {
+        let min = self.min_value.unwrap_or(default_min);
+        let max = self.max_value.unwrap_or(default_max).max(min);
+        value.clamp(min, max)
+    }
+}
+
+fn parse_chat_number(raw: &str) -> Result<f32, String> {
+    raw.parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("'{}' isn't a number", raw))
+}
+
+/// Version of the drip pack JSON layout this build writes.
+pub const DRIP_PACK_FORMAT: u32 = 1;
+
+/// A shareable bundle of OSC toggles and the channel point redeems that fire them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DripPack {
+    #[serde(default = "default_pack_format")]
+    pub format: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The avatar the pack was exported from, as a hint when importing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_name: Option<String>,
+    pub toggles: Vec<DripPackToggle>,
+}
+
+fn default_pack_format() -> u32 {
+    DRIP_PACK_FORMAT
+}
+
+/// One toggle in a drip pack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DripPackToggle {
+    pub parameter_name: String,
+    pub parameter_type: String,
+    pub on_value: String,
+    pub off_value: String,
+    #[serde(default)]
+    pub duration_seconds: Option<i32>,
+    #[serde(default)]
+    pub cooldown_seconds: i32,
+    pub reward_name: String,
+    pub cost: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub is_input_required: bool,
+    /// Reward background as `#RRGGBB`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    /// Reward image. Twitch can't set images through its API, so this is only
+    /// shown to whoever imports the pack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn control(parameter_type: &str, min_role: &str, min: Option<f32>, max: Option<f32>) -> OscChatControl {
+        OscChatControl {
+            id: 1,
+            name: "ears".to_string(),
+            parameter_name: "Ears".to_string(),
+            parameter_type: parameter_type.to_string(),
+            min_role: min_role.to_string(),
+            min_value: min,
+            max_value: max,
+            cooldown_seconds: 0,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_chat_values_are_clamped_and_roles_ranked() {
+        let int = control("int", "vip", Some(2.0), Some(10.0));
+        assert!(matches!(int.value_from_chat("50"), Ok(OscParameterValue::Int(10))));
+        assert!(matches!(int.value_from_chat("-3"), Ok(OscParameterValue::Int(2))));
+        assert!(matches!(int.value_from_chat("4.6"), Ok(OscParameterValue::Int(5))));
+        assert!(int.value_from_chat("lots").is_err());
+
+        let float = control("float", "everyone", None, None);
+        assert!(matches!(float.value_from_chat("3"), Ok(OscParameterValue::Float(v)) if v == 1.0));
+        assert!(float.value_from_chat("NaN").is_err());
+        assert!(matches!(control("bool", "everyone", None, None).value_from_chat("ON"), Ok(OscParameterValue::Bool(true))));
+
+        assert!(!int.allows(&["subscriber".to_string()]));
+        assert!(int.allows(&["vip".to_string()]));
+        assert!(int.allows(&["mod".to_string()]));
+        assert!(float.allows(&[]));
+        assert!(!control("bool", "nobody", None, None).allows(&["moderator".to_string()]));
+        assert!(control("bool", "nobody", None, None).validate().is_err());
+    }
+}
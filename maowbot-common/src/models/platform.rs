@@ -21,7 +21,8 @@ pub enum Platform {
     #[sqlx(rename = "twitch-eventsub")]
     TwitchEventSub,
     #[sqlx(rename = "obs")]
-    OBS
+    OBS,
+    Kick,
 }
 
 impl fmt::Display for Platform {
@@ -33,6 +34,7 @@ impl fmt::Display for Platform {
             Platform::TwitchIRC => write!(f, "twitch-irc"),
             Platform::TwitchEventSub => write!(f, "twitch-eventsub"),
             Platform::OBS => write!(f, "obs"),
+            Platform::Kick => write!(f, "kick"),
         }
     }
 }
@@ -48,6 +50,7 @@ impl FromStr for Platform {
             "twitch-irc" => Ok(Platform::TwitchIRC),
             "twitch-eventsub" => Ok(Platform::TwitchEventSub),
             "obs" => Ok(Platform::OBS),
+            "kick" => Ok(Platform::Kick),
             _ => Err(format!("Unknown platform: {}", s)),
         }
     }
@@ -119,7 +122,7 @@ pub struct PlatformConfigData {
 }
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlatformConfig {
     pub platform_config_id: Uuid,
     pub platform: String,
@@ -0,0 +1,251 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Why a raid-defense incident started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IncidentTrigger {
+    /// Too many messages per second
+    MessageRate,
+    /// Many chatters sending the same text
+    IdenticalMessages,
+    /// Too many chatters with freshly created accounts
+    NewAccounts,
+    /// Too many chatters that bot detection scored as likely bots
+    LikelyBots,
+    /// Started by a moderator
+    Manual,
+}
+
+impl fmt::Display for IncidentTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncidentTrigger::MessageRate => write!(f, "message_rate"),
+            IncidentTrigger::IdenticalMessages => write!(f, "identical_messages"),
+            IncidentTrigger::NewAccounts => write!(f, "new_accounts"),
+            IncidentTrigger::LikelyBots => write!(f, "likely_bots"),
+            IncidentTrigger::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+impl FromStr for IncidentTrigger {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "message_rate" => Ok(IncidentTrigger::MessageRate),
+            "identical_messages" => Ok(IncidentTrigger::IdenticalMessages),
+            "new_accounts" => Ok(IncidentTrigger::NewAccounts),
+            "likely_bots" => Ok(IncidentTrigger::LikelyBots),
+            "manual" => Ok(IncidentTrigger::Manual),
+            other => Err(Error::Parse(format!("Unknown incident trigger '{}'", other))),
+        }
+    }
+}
+
+/// A chat message kept as evidence for an incident.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedMessage {
+    /// Twitch user id
+    pub platform_user_id: String,
+    pub username: String,
+    pub text: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// A period during which chat was locked down.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectionIncident {
+    pub incident_id: Uuid,
+    pub channel: String,
+    pub trigger: IncidentTrigger,
+    /// Human-readable reason, e.g. "42 messages in 5s"
+    pub details: String,
+    /// What was switched on, e.g. "followers_only:10m", "shield_mode"
+    pub actions: Vec<String>,
+    pub messages: Vec<CapturedMessage>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    /// "auto" or who lifted it
+    pub ended_by: Option<String>,
+}
+
+/// Limits for the spike heuristic; a limit of 0 turns that check off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpikeThresholds {
+    pub window_secs: i64,
+    pub messages_per_sec: i64,
+    /// Distinct chatters sending the same text within the window
+    pub identical_messages: usize,
+    /// Accounts younger than this count as new
+    pub new_account_days: i64,
+    pub new_account_percent: i64,
+    /// Fewer distinct chatters than this never trips the new-account or likely-bot check
+    pub min_chatters: usize,
+    pub likely_bot_percent: i64,
+    /// Bot scores at or above this count as likely bots
+    pub likely_bot_score: f32,
+}
+
+/// Which check tripped, with a short description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spike {
+    pub trigger: IncidentTrigger,
+    pub details: String,
+}
+
+/// Lowercases and collapses whitespace so trivially varied copypasta still matches.
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Checks the messages of the last `window_secs` against the thresholds.
+/// `account_created` maps Twitch user ids to account creation times; chatters
+/// not in it are left out of the new-account ratio. `bot_scores` does the same
+/// for the likely-bot ratio with scores from bot detection.
+pub fn detect_spike(
+    window: &[CapturedMessage],
+    account_created: &HashMap<String, DateTime<Utc>>,
+    bot_scores: &HashMap<String, f32>,
+    thresholds: &SpikeThresholds,
+    now: DateTime<Utc>,
+) -> Option<Spike> {
+    let since = now - Duration::seconds(thresholds.window_secs.max(1));
+    let recent: Vec<&CapturedMessage> = window.iter().filter(|m| m.sent_at >= since).collect();
+    if recent.is_empty() {
+        return None;
+    }
+
+    if thresholds.messages_per_sec > 0
+        && recent.len() as i64 > thresholds.messages_per_sec * thresholds.window_secs.max(1)
+    {
+        return Some(Spike {
+            trigger: IncidentTrigger::MessageRate,
+            details: format!("{} messages in {}s", recent.len(), thresholds.window_secs.max(1)),
+        });
+    }
+
+    if thresholds.identical_messages > 0 {
+        let mut senders: HashMap<String, HashSet<&str>> = HashMap::new();
+        for m in &recent {
+            let text = normalize_text(&m.text);
+            if !text.is_empty() {
+                senders.entry(text).or_default().insert(m.platform_user_id.as_str());
+            }
+        }
+        if let Some((text, users)) = senders.iter()
+            .filter(|(_, users)| users.len() >= thresholds.identical_messages)
+            .max_by_key(|(_, users)| users.len())
+        {
+            let preview: String = text.chars().take(40).collect();
+            return Some(Spike {
+                trigger: IncidentTrigger::IdenticalMessages,
+                details: format!("{} chatters sent \"{}\"", users.len(), preview),
+            });
+        }
+    }
+
+    if thresholds.new_account_percent > 0 {
+        let young_since = now - Duration::days(thresholds.new_account_days.max(0));
+        let chatters: HashSet<&str> = recent.iter().map(|m| m.platform_user_id.as_str()).collect();
+        let known: Vec<DateTime<Utc>> = chatters.iter()
+            .filter_map(|id| account_created.get(*id).copied())
+            .collect();
+        if known.len() >= thresholds.min_chatters.max(1) {
+            let young = known.iter().filter(|created| **created >= young_since).count();
+            if young as i64 * 100 >= thresholds.new_account_percent * known.len() as i64 {
+                return Some(Spike {
+                    trigger: IncidentTrigger::NewAccounts,
+                    details: format!(
+                        "{} of {} chatters have accounts under {} days old",
+                        young, known.len(), thresholds.new_account_days
+                    ),
+                });
+            }
+        }
+    }
+
+    if thresholds.likely_bot_percent > 0 {
+        let chatters: HashSet<&str> = recent.iter().map(|m| m.platform_user_id.as_str()).collect();
+        let scored: Vec<f32> = chatters.iter().filter_map(|id| bot_scores.get(*id).copied()).collect();
+        if scored.len() >= thresholds.min_chatters.max(1) {
+            let bots = scored.iter().filter(|s| **s >= thresholds.likely_bot_score).count();
+            if bots as i64 * 100 >= thresholds.likely_bot_percent * scored.len() as i64 {
+                return Some(Spike {
+                    trigger: IncidentTrigger::LikelyBots,
+                    details: format!("{} of {} scored chatters look like bots", bots, scored.len()),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> SpikeThresholds {
+        SpikeThresholds {
+            window_secs: 10,
+            messages_per_sec: 5,
+            identical_messages: 3,
+            new_account_days: 7,
+            new_account_percent: 50,
+            min_chatters: 4,
+            likely_bot_percent: 50,
+            likely_bot_score: 0.7,
+        }
+    }
+
+    fn msg(user: &str, text: &str, at: DateTime<Utc>) -> CapturedMessage {
+        CapturedMessage {
+            platform_user_id: user.to_string(),
+            username: user.to_string(),
+            text: text.to_string(),
+            sent_at: at,
+        }
+    }
+
+    #[test]
+    fn test_detects_each_kind_of_spike() {
+        let now = Utc::now();
+        let none = HashMap::new();
+        let none_scored = HashMap::new();
+
+        let flood: Vec<_> = (0..51).map(|i| msg(&i.to_string(), &format!("hi {i}"), now)).collect();
+        assert_eq!(detect_spike(&flood, &none, &none_scored, &thresholds(), now).unwrap().trigger, IncidentTrigger::MessageRate);
+        // Messages older than the window don't count
+        let old: Vec<_> = flood.iter().map(|m| msg(&m.platform_user_id, &m.text, now - Duration::seconds(30))).collect();
+        assert!(detect_spike(&old, &none, &none_scored, &thresholds(), now).is_none());
+
+        // One user repeating themselves is not a raid; three users are
+        let same_user: Vec<_> = (0..5).map(|_| msg("a", "FOLLOW  my channel", now)).collect();
+        assert!(detect_spike(&same_user, &none, &none_scored, &thresholds(), now).is_none());
+        let copypasta = vec![msg("a", "follow my channel", now), msg("b", "FOLLOW  my channel", now), msg("c", "Follow my channel", now)];
+        assert_eq!(detect_spike(&copypasta, &none, &none_scored, &thresholds(), now).unwrap().trigger, IncidentTrigger::IdenticalMessages);
+
+        let chatters: Vec<_> = ["a", "b", "c", "d"].iter().map(|u| msg(u, u, now)).collect();
+        let mut created = HashMap::new();
+        created.insert("a".to_string(), now - Duration::days(1));
+        created.insert("b".to_string(), now - Duration::days(2));
+        created.insert("c".to_string(), now - Duration::days(400));
+        assert!(detect_spike(&chatters, &created, &none_scored, &thresholds(), now).is_none(), "below min_chatters");
+        created.insert("d".to_string(), now - Duration::days(900));
+        assert_eq!(detect_spike(&chatters, &created, &none_scored, &thresholds(), now).unwrap().trigger, IncidentTrigger::NewAccounts);
+
+        let scores: HashMap<String, f32> = [("a", 0.9), ("b", 0.8), ("c", 0.1), ("d", 0.0)]
+            .iter().map(|(u, s)| (u.to_string(), *s)).collect();
+        assert_eq!(detect_spike(&chatters, &none, &scores, &thresholds(), now).unwrap().trigger, IncidentTrigger::LikelyBots);
+    }
+}
@@ -35,6 +35,11 @@ pub struct Redeem {
     /// Will be used for user interactions, especially with AI-related redeems.
     #[serde(default)]
     pub redeem_prompt_text: Option<String>,
+
+    /// Redemptions wait in the approval queue until the streamer approves
+    /// or denies them; denied and expired ones are refunded.
+    #[serde(default)]
+    pub requires_approval: bool,
 }
 
 /// Tracks usage of a given redeem by a user.
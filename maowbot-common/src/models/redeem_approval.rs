@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Where a redemption waiting for approval stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Denied,
+    /// Nobody decided before the approval timeout
+    Expired,
+}
+
+impl fmt::Display for ApprovalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalStatus::Pending => write!(f, "pending"),
+            ApprovalStatus::Approved => write!(f, "approved"),
+            ApprovalStatus::Denied => write!(f, "denied"),
+            ApprovalStatus::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+impl FromStr for ApprovalStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(ApprovalStatus::Pending),
+            "approved" => Ok(ApprovalStatus::Approved),
+            "denied" => Ok(ApprovalStatus::Denied),
+            "expired" => Ok(ApprovalStatus::Expired),
+            other => Err(Error::Parse(format!("Unknown approval status '{}'", other))),
+        }
+    }
+}
+
+/// A redemption of a redeem with `requires_approval` set. It runs only once
+/// approved; denied and expired ones are refunded on Twitch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedeemApproval {
+    pub approval_id: Uuid,
+    pub redeem_id: Uuid,
+    /// Copied from the redeem so the queue reads well after renames
+    pub reward_name: String,
+    pub platform: String,
+    pub channel: String,
+    /// The bot's user for the redeemer
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub user_input: String,
+    /// The Twitch redemption as received, replayed when approved
+    pub redemption: serde_json::Value,
+    pub status: ApprovalStatus,
+    pub requested_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Who approved or denied it; None for expired ones
+    pub decided_by: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trips_through_its_name() {
+        for status in [ApprovalStatus::Pending, ApprovalStatus::Approved, ApprovalStatus::Denied, ApprovalStatus::Expired] {
+            assert_eq!(status.to_string().parse::<ApprovalStatus>().unwrap(), status);
+        }
+        assert_eq!("DENIED".parse::<ApprovalStatus>().unwrap(), ApprovalStatus::Denied);
+        assert!("maybe".parse::<ApprovalStatus>().is_err());
+    }
+}
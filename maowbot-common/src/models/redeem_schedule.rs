@@ -0,0 +1,97 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// When a redeem schedule applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScheduleCondition {
+    /// A weekly window such as "mon-fri 18:00-22:00 Europe/Berlin"
+    Time,
+    /// While the stream category matches, case-insensitively
+    Category,
+    /// While a hype train is running
+    HypeTrain,
+}
+
+impl fmt::Display for ScheduleCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleCondition::Time => write!(f, "time"),
+            ScheduleCondition::Category => write!(f, "category"),
+            ScheduleCondition::HypeTrain => write!(f, "hype_train"),
+        }
+    }
+}
+
+impl FromStr for ScheduleCondition {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "time" | "window" => Ok(ScheduleCondition::Time),
+            "category" | "game" => Ok(ScheduleCondition::Category),
+            "hype_train" | "hype" | "hypetrain" => Ok(ScheduleCondition::HypeTrain),
+            other => Err(Error::Parse(format!("Unknown schedule condition '{}'", other))),
+        }
+    }
+}
+
+/// What a schedule does to its reward while it applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScheduleAction {
+    Enable,
+    Disable,
+    /// Uses `amount` as the cost
+    SetCost,
+    /// Multiplies the cost by `amount`
+    MultiplyCost,
+}
+
+impl fmt::Display for ScheduleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleAction::Enable => write!(f, "enable"),
+            ScheduleAction::Disable => write!(f, "disable"),
+            ScheduleAction::SetCost => write!(f, "set_cost"),
+            ScheduleAction::MultiplyCost => write!(f, "multiply_cost"),
+        }
+    }
+}
+
+impl FromStr for ScheduleAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "enable" => Ok(ScheduleAction::Enable),
+            "disable" => Ok(ScheduleAction::Disable),
+            "set_cost" | "cost" => Ok(ScheduleAction::SetCost),
+            "multiply_cost" | "multiply" => Ok(ScheduleAction::MultiplyCost),
+            other => Err(Error::Parse(format!("Unknown schedule action '{}'", other))),
+        }
+    }
+}
+
+/// A rule that changes a channel point reward while its condition holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedeemSchedule {
+    pub schedule_id: Uuid,
+    pub redeem_id: Uuid,
+    /// Unique per redeem
+    pub name: String,
+    pub condition: ScheduleCondition,
+    /// The time window or category; empty for hype trains
+    pub condition_value: String,
+    pub action: ScheduleAction,
+    /// The cost for `SetCost`, the factor for `MultiplyCost`
+    pub amount: Option<f64>,
+    /// Higher priorities are applied later, so they win
+    pub priority: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// How a responder's pattern is matched against a chat line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResponderMatch {
+    /// A regular expression, case-insensitive
+    Regex,
+    /// Any of the `|`-separated keywords, matched as whole words
+    AnyKeyword,
+    /// Every one of the `|`-separated keywords, in any order
+    AllKeywords,
+}
+
+impl fmt::Display for ResponderMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponderMatch::Regex => write!(f, "regex"),
+            ResponderMatch::AnyKeyword => write!(f, "any"),
+            ResponderMatch::AllKeywords => write!(f, "all"),
+        }
+    }
+}
+
+impl FromStr for ResponderMatch {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "regex" => Ok(ResponderMatch::Regex),
+            "any" | "keyword" | "keywords" => Ok(ResponderMatch::AnyKeyword),
+            "all" => Ok(ResponderMatch::AllKeywords),
+            other => Err(Error::Parse(format!("Unknown match kind '{}'", other))),
+        }
+    }
+}
+
+/// A chat responder: when a line matches, one of its responses is sent back
+/// and `responder.triggered` is published for pipelines to act on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Responder {
+    pub responder_id: Uuid,
+    /// Unique
+    pub name: String,
+    pub match_kind: ResponderMatch,
+    pub pattern: String,
+    /// Templates with {user}, {channel} and {match}; one is picked at random.
+    /// Empty to only publish the event.
+    pub responses: Vec<String>,
+    /// "twitch-irc", "discord" or "kick"; None answers on every platform
+    pub platform: Option<String>,
+    /// Limits the responder to one channel; None for all
+    pub channel: Option<String>,
+    /// Chance of answering a match, 1-100
+    pub chance_percent: i32,
+    /// Quiet time after answering anyone
+    pub cooldown_secs: i32,
+    /// Quiet time after answering the same chatter
+    pub user_cooldown_secs: i32,
+    pub enabled: bool,
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use crate::error::Error;
+
+/// Value type of a bot_config setting. Everything is stored as a string; the
+/// type decides how the value is validated and parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettingType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Json,
+    /// One of `allowed_values`
+    Enum,
+}
+
+impl SettingType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingType::String => "string",
+            SettingType::Integer => "integer",
+            SettingType::Float => "float",
+            SettingType::Boolean => "boolean",
+            SettingType::Json => "json",
+            SettingType::Enum => "enum",
+        }
+    }
+}
+
+/// Schema entry for one known bot_config key.
+#[derive(Debug, Clone)]
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+    pub setting_type: SettingType,
+    pub default: Option<&'static str>,
+    pub allowed_values: &'static [&'static str],
+    /// Inclusive bounds for Integer settings
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub is_secret: bool,
+    /// Only read at startup; a new value takes effect after a restart
+    pub requires_restart: bool,
+}
+
+impl SettingDefinition {
+    /// Checks that `value` parses as this setting's type and is in range.
+    pub fn validate(&self, value: &str) -> Result<(), Error> {
+        let invalid = |reason: String| Error::ValidationError(format!("{}: {}", self.key, reason));
+        match self.setting_type {
+            SettingType::String => Ok(()),
+            SettingType::Integer => {
+                let n: i64 = value.trim().parse()
+                    .map_err(|_| invalid(format!("'{}' is not an integer", value)))?;
+                if let Some(min) = self.min {
+                    if n < min {
+                        return Err(invalid(format!("{} is below the minimum of {}", n, min)));
+                    }
+                }
+                if let Some(max) = self.max {
+                    if n > max {
+                        return Err(invalid(format!("{} is above the maximum of {}", n, max)));
+                    }
+                }
+                Ok(())
+            }
+            SettingType::Float => value.trim().parse::<f64>()
+                .map(|_| ())
+                .map_err(|_| invalid(format!("'{}' is not a number", value))),
+            SettingType::Boolean => parse_bool(value)
+                .map(|_| ())
+                .ok_or_else(|| invalid(format!("'{}' is not a boolean (true/false)", value))),
+            SettingType::Json => serde_json::from_str::<serde_json::Value>(value)
+                .map(|_| ())
+                .map_err(|e| invalid(format!("invalid JSON: {}", e))),
+            SettingType::Enum => {
+                if self.allowed_values.iter().any(|v| v.eq_ignore_ascii_case(value.trim())) {
+                    Ok(())
+                } else {
+                    Err(invalid(format!(
+                        "'{}' is not one of: {}",
+                        value,
+                        self.allowed_values.join(", ")
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Accepts the spellings the TUI and older configs have used for booleans.
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
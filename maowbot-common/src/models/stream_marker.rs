@@ -0,0 +1,154 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// What caused a stream marker to be placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarkerSource {
+    Raid,
+    Redeem,
+    Command,
+    Scene,
+    Manual,
+    Hype,
+}
+
+impl fmt::Display for MarkerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkerSource::Raid => write!(f, "raid"),
+            MarkerSource::Redeem => write!(f, "redeem"),
+            MarkerSource::Command => write!(f, "command"),
+            MarkerSource::Scene => write!(f, "scene"),
+            MarkerSource::Manual => write!(f, "manual"),
+            MarkerSource::Hype => write!(f, "hype"),
+        }
+    }
+}
+
+impl FromStr for MarkerSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "raid" => Ok(MarkerSource::Raid),
+            "redeem" => Ok(MarkerSource::Redeem),
+            "command" => Ok(MarkerSource::Command),
+            "scene" => Ok(MarkerSource::Scene),
+            "manual" => Ok(MarkerSource::Manual),
+            "hype" => Ok(MarkerSource::Hype),
+            other => Err(Error::Parse(format!("Unknown marker source '{}'", other))),
+        }
+    }
+}
+
+/// A Helix stream marker, kept so a chapter list can be built once the stream ends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamMarker {
+    pub marker_id: Uuid,
+    pub broadcaster_id: String,
+    pub stream_id: String,
+    /// Twitch's id for the marker
+    pub twitch_marker_id: Option<String>,
+    /// Offset into the broadcast
+    pub position_seconds: i32,
+    pub description: String,
+    pub source: MarkerSource,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The chapter list exported for one broadcast, keyed by its stream id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VodChapters {
+    pub stream_id: String,
+    pub broadcaster_id: String,
+    /// The archived VOD, once Twitch has one
+    pub video_id: Option<String>,
+    pub video_url: Option<String>,
+    pub title: String,
+    /// One "H:MM:SS description" line per chapter
+    pub chapters: String,
+    pub marker_count: i32,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// Chapters closer together than this are dropped, as YouTube ignores them.
+pub const MIN_CHAPTER_GAP_SECONDS: i32 = 10;
+
+/// "M:SS" under an hour, "H:MM:SS" after.
+pub fn format_timestamp(seconds: i32) -> String {
+    let seconds = seconds.max(0);
+    let (h, m, s) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+/// Builds an editor-friendly chapter list: it always starts at 0:00 (with
+/// `intro` unless a marker is already there), is sorted by position, and skips
+/// markers within `MIN_CHAPTER_GAP_SECONDS` of the previous chapter.
+pub fn chapter_list(markers: &[StreamMarker], intro: &str) -> String {
+    let mut sorted: Vec<&StreamMarker> = markers.iter().collect();
+    sorted.sort_by_key(|m| m.position_seconds);
+
+    let mut lines: Vec<(i32, &str)> = Vec::new();
+    match sorted.first() {
+        Some(first) if first.position_seconds < MIN_CHAPTER_GAP_SECONDS => {
+            lines.push((0, &first.description));
+            sorted.remove(0);
+        }
+        _ => lines.push((0, intro)),
+    }
+    for marker in sorted {
+        let last = lines.last().map(|(pos, _)| *pos).unwrap_or(0);
+        if marker.position_seconds - last >= MIN_CHAPTER_GAP_SECONDS {
+            lines.push((marker.position_seconds, &marker.description));
+        }
+    }
+
+    lines.iter()
+        .map(|(pos, desc)| format!("{} {}", format_timestamp(*pos), desc))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(position_seconds: i32, description: &str) -> StreamMarker {
+        StreamMarker {
+            marker_id: Uuid::new_v4(),
+            broadcaster_id: "1".into(),
+            stream_id: "2".into(),
+            twitch_marker_id: None,
+            position_seconds,
+            description: description.into(),
+            source: MarkerSource::Manual,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_builds_chapter_list() {
+        assert_eq!(format_timestamp(65), "1:05");
+        assert_eq!(format_timestamp(3723), "1:02:03");
+
+        let markers = vec![
+            marker(3723, "Raid from foo"),
+            marker(600, "Scene: Gaming"),
+            marker(605, "Too close"),
+        ];
+        assert_eq!(
+            chapter_list(&markers, "Start"),
+            "0:00 Start\n10:00 Scene: Gaming\n1:02:03 Raid from foo"
+        );
+        assert_eq!(chapter_list(&[marker(3, "Intro")], "Start"), "0:00 Intro");
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Running totals for one stream. Chat counts only the broadcaster's channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub chat_messages: i64,
+    pub unique_chatters: i64,
+    pub follows: i64,
+    /// New subs and resubs, not counting gifted ones
+    pub subs: i64,
+    pub gifted_subs: i64,
+    pub bits: i64,
+    /// Raids into the channel
+    pub raids: i64,
+    pub redemptions: i64,
+    pub hype_moments: i64,
+    pub peak_viewers: i64,
+}
+
+/// One broadcast, from stream.online to stream.offline. Markers attach to it
+/// through `stream_id`; chat messages and events through its time window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamSession {
+    pub session_id: Uuid,
+    pub platform: String,
+    /// Lowercase channel login, without '#'
+    pub channel: String,
+    pub broadcaster_id: String,
+    /// Twitch's id for the broadcast
+    pub stream_id: String,
+    pub title: String,
+    pub category: String,
+    pub started_at: DateTime<Utc>,
+    /// None while the stream is live
+    pub ended_at: Option<DateTime<Utc>>,
+    pub stats: SessionStats,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StreamSession {
+    pub fn is_live(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Start and end of the session; a live session ends now.
+    pub fn window(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (self.started_at, self.ended_at.unwrap_or_else(Utc::now))
+    }
+
+    pub fn duration_seconds(&self) -> i64 {
+        let (start, end) = self.window();
+        (end - start).num_seconds().max(0)
+    }
+}
+
+/// How many messages one viewer sent during a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionChatter {
+    pub twitch_user_id: String,
+    pub display_name: String,
+    pub messages: i64,
+}
+
+/// What analytics, recaps and leaderboards are scoped to: "this stream",
+/// the last one, a given one, or all time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionScope {
+    /// The live session, if any
+    Current,
+    /// The live session, or the last one to end
+    Latest,
+    Id(Uuid),
+    AllTime,
+}
+
+impl fmt::Display for SessionScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionScope::Current => write!(f, "current"),
+            SessionScope::Latest => write!(f, "last"),
+            SessionScope::Id(id) => write!(f, "{}", id),
+            SessionScope::AllTime => write!(f, "all"),
+        }
+    }
+}
+
+impl FromStr for SessionScope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "current" | "this" | "live" => Ok(SessionScope::Current),
+            "last" | "latest" | "previous" => Ok(SessionScope::Latest),
+            "all" | "alltime" | "all_time" | "all-time" => Ok(SessionScope::AllTime),
+            other => Uuid::parse_str(other)
+                .map(SessionScope::Id)
+                .map_err(|_| Error::Parse(format!(
+                    "Unknown session '{}', expected current, last, all or a session id", s.trim()
+                ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_scopes() {
+        assert_eq!("".parse::<SessionScope>().unwrap(), SessionScope::Current);
+        assert_eq!("Last".parse::<SessionScope>().unwrap(), SessionScope::Latest);
+        assert_eq!("all".parse::<SessionScope>().unwrap(), SessionScope::AllTime);
+        let id = Uuid::new_v4();
+        assert_eq!(id.to_string().parse::<SessionScope>().unwrap(), SessionScope::Id(id));
+        assert!("yesterday".parse::<SessionScope>().is_err());
+    }
+}
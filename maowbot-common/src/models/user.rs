@@ -11,6 +11,31 @@ pub struct User {
     pub is_active: bool,
 }
 
+/// Column a user listing is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UserSort {
+    #[default]
+    CreatedAt,
+    LastSeen,
+    Username,
+}
+
+/// Filters, sort and window for `UserRepo::list_page`.
+#[derive(Debug, Clone, Default)]
+pub struct UserListQuery {
+    pub active_only: bool,
+    /// Only users with an identity on one of these platforms (e.g. "twitch-irc")
+    pub platforms: Vec<String>,
+    /// Only users holding one of these roles on any platform
+    pub roles: Vec<String>,
+    /// Case-insensitive prefix of the global username
+    pub name_prefix: Option<String>,
+    pub sort: UserSort,
+    pub descending: bool,
+    pub offset: i64,
+    pub limit: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct UserAuditLogEntry {
     pub audit_id: Uuid,
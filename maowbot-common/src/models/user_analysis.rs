@@ -1,5 +1,6 @@
 // File: src/models/user_analysis.rs
 
+use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
@@ -14,6 +15,12 @@ pub struct UserAnalysis {
     pub horni_score: f32,
     pub ai_notes: Option<String>,
     pub moderator_notes: Option<String>,
+    /// 0.0-1.0, how likely the account is a bot (see `score_bot`)
+    pub bot_score: f32,
+    /// Why, e.g. "account 3 hours old"
+    pub bot_signals: Vec<String>,
+    /// When bot detection last looked at the account
+    pub bot_scored_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -30,9 +37,156 @@ impl UserAnalysis {
             horni_score: 0.0,
             ai_notes: None,
             moderator_notes: None,
+            bot_score: 0.0,
+            bot_signals: Vec::new(),
+            bot_scored_at: None,
             created_at: now,
             updated_at: now,
         }
     }
 }
 
+
+/// Phrases follow-bot and viewbot sellers use in their first messages.
+const PROMO_PHRASES: &[&str] = &[
+    "best viewers", "cheap viewers", "viewers on", "followers, primes", "followers and viewers",
+    "primes and viewers", "promote your stream", "grow your channel", "become famous",
+    "dogehype", "streamboo", "bigfollows", "viewbot",
+];
+
+/// What bot detection knows about a new chatter.
+#[derive(Debug, Clone)]
+pub struct BotEvidence {
+    pub username: String,
+    /// From Twitch; None when it couldn't be asked
+    pub account_created: Option<DateTime<Utc>>,
+    /// When they followed the channel, if they do
+    pub followed_at: Option<DateTime<Utc>>,
+    pub first_message_at: DateTime<Utc>,
+    /// Their first few messages
+    pub messages: Vec<String>,
+}
+
+/// The outcome of `score_bot`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotScore {
+    /// 0.0-1.0
+    pub score: f32,
+    pub signals: Vec<String>,
+}
+
+/// Shannon entropy of `text` in bits per character.
+pub fn char_entropy(text: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut total = 0usize;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        *counts.entry(c).or_default() += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    counts.values()
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Adds up the bot heuristics for one chatter: a young account, following
+/// right before chatting, follow-bot advertising, links, repetitive or
+/// low-entropy messages and a generated-looking name.
+pub fn score_bot(evidence: &BotEvidence) -> BotScore {
+    let mut score = 0.0f32;
+    let mut signals = Vec::new();
+    let mut add = |weight: f32, signal: String| {
+        score += weight;
+        signals.push(signal);
+    };
+
+    if let Some(created) = evidence.account_created {
+        let age = evidence.first_message_at - created;
+        if age.num_hours() < 24 {
+            add(0.35, format!("account {} hours old", age.num_hours().max(0)));
+        } else if age.num_days() < 7 {
+            add(0.25, format!("account {} days old", age.num_days()));
+        } else if age.num_days() < 30 {
+            add(0.1, format!("account {} days old", age.num_days()));
+        }
+    }
+
+    if let Some(followed) = evidence.followed_at {
+        let before = (evidence.first_message_at - followed).num_seconds();
+        if (0..=120).contains(&before) {
+            add(0.15, format!("followed {}s before first message", before));
+        }
+    }
+
+    let lowered: Vec<String> = evidence.messages.iter().map(|m| m.to_lowercase()).collect();
+    if let Some(phrase) = PROMO_PHRASES.iter().find(|p| lowered.iter().any(|m| m.contains(*p))) {
+        add(0.4, format!("advertises \"{}\"", phrase));
+    }
+    if lowered.iter().any(|m| m.contains("http://") || m.contains("https://") || m.contains("www.")) {
+        add(0.1, "posts links".to_string());
+    }
+
+    let normalized: Vec<String> = lowered.iter()
+        .map(|m| m.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    if normalized.len() >= 2 && normalized.iter().all(|m| *m == normalized[0]) {
+        add(0.15, "repeats the same message".to_string());
+    }
+    let long: Vec<&String> = evidence.messages.iter().filter(|m| m.chars().count() >= 10).collect();
+    if !long.is_empty() {
+        let entropy = long.iter().map(|m| char_entropy(m)).sum::<f64>() / long.len() as f64;
+        if entropy < 2.5 {
+            add(0.15, format!("low-entropy messages ({:.1} bits/char)", entropy));
+        }
+    }
+
+    let name = evidence.username.to_lowercase();
+    let digits = name.chars().rev().take_while(|c| c.is_ascii_digit()).count();
+    if digits >= 4 && digits < name.chars().count() {
+        add(0.1, "generated-looking name".to_string());
+    }
+
+    BotScore { score: score.min(1.0), signals }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_scores_obvious_bots_high_and_regulars_low() {
+        let now = Utc::now();
+        let bot = score_bot(&BotEvidence {
+            username: "viewerking48213".into(),
+            account_created: Some(now - Duration::hours(2)),
+            followed_at: Some(now - Duration::seconds(20)),
+            first_message_at: now,
+            messages: vec!["Best viewers on streamboo .com".into()],
+        });
+        assert!(bot.score >= 0.9, "{:?}", bot);
+        assert_eq!(bot.signals.len(), 4);
+
+        let regular = score_bot(&BotEvidence {
+            username: "cozy_cat".into(),
+            account_created: Some(now - Duration::days(900)),
+            followed_at: Some(now - Duration::days(30)),
+            first_message_at: now,
+            messages: vec!["hi everyone, how was your day?".into(), "that boss fight was wild".into()],
+        });
+        assert_eq!(regular.score, 0.0);
+        assert!(regular.signals.is_empty());
+    }
+
+    #[test]
+    fn test_entropy_flags_keyboard_mashing() {
+        assert_eq!(char_entropy(""), 0.0);
+        assert!(char_entropy("aaaaaaaaaaaa") < 0.1);
+        assert!(char_entropy("the quick brown fox jumps") > 3.5);
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// A timestamped note a moderator attached to a user.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserNote {
+    pub note_id: Uuid,
+    pub user_id: Uuid,
+    /// Moderator (or API token name) who wrote the note
+    pub author: String,
+    pub note_text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserNote {
+    pub fn new(user_id: Uuid, author: &str, note_text: &str) -> Self {
+        Self {
+            note_id: Uuid::new_v4(),
+            user_id,
+            author: author.to_string(),
+            note_text: note_text.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationActionType {
+    Timeout,
+    Ban,
+    Unban,
+    Warning,
+    /// Clears a user's offenses in a category, so the escalation ladder starts over
+    Pardon,
+}
+
+impl fmt::Display for ModerationActionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModerationActionType::Timeout => write!(f, "timeout"),
+            ModerationActionType::Ban => write!(f, "ban"),
+            ModerationActionType::Unban => write!(f, "unban"),
+            ModerationActionType::Warning => write!(f, "warning"),
+            ModerationActionType::Pardon => write!(f, "pardon"),
+        }
+    }
+}
+
+impl FromStr for ModerationActionType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "timeout" => Ok(ModerationActionType::Timeout),
+            "ban" => Ok(ModerationActionType::Ban),
+            "unban" => Ok(ModerationActionType::Unban),
+            "warning" | "warn" => Ok(ModerationActionType::Warning),
+            "pardon" => Ok(ModerationActionType::Pardon),
+            other => Err(Error::Parse(format!(
+                "Unknown moderation action '{}' (expected timeout, ban, unban, warning or pardon)", other
+            ))),
+        }
+    }
+}
+
+/// One timeout, ban, unban, warning or pardon for a user on some platform.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationAction {
+    pub action_id: Uuid,
+    pub user_id: Uuid,
+    pub platform: String,
+    /// Empty when the action is not tied to a channel
+    pub channel: String,
+    pub action_type: ModerationActionType,
+    pub reason: Option<String>,
+    /// Only set for timeouts
+    pub duration_seconds: Option<i32>,
+    pub moderator: String,
+    /// The moderation rule category it counts toward on the escalation
+    /// ladder; empty for actions outside the ladder
+    pub category: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ModerationAction {
+    pub fn new(
+        user_id: Uuid,
+        platform: &str,
+        channel: &str,
+        action_type: ModerationActionType,
+        moderator: &str,
+    ) -> Self {
+        Self {
+            action_id: Uuid::new_v4(),
+            user_id,
+            platform: platform.to_string(),
+            channel: channel.to_string(),
+            action_type,
+            reason: None,
+            duration_seconds: None,
+            moderator: moderator.to_string(),
+            category: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long one Twitch viewer has been in a channel's chat while it was live,
+/// as counted from the chatters list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewerWatchtime {
+    /// Lowercase channel login, without '#'
+    pub channel: String,
+    pub twitch_user_id: String,
+    /// Lowercase login, as last seen
+    pub user_login: String,
+    pub display_name: String,
+    pub watch_seconds: i64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
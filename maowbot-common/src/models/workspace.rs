@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The built-in workspace that pre-workspace data and chat runtimes use.
+pub const DEFAULT_WORKSPACE_ID: Uuid = Uuid::nil();
+pub const DEFAULT_WORKSPACE_NAME: &str = "default";
+
+/// A chat channel routed to a workspace: its commands, redeems, config and
+/// credentials answer there. Unrouted channels use the default workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceChannel {
+    pub workspace_id: Uuid,
+    /// `channel_platform` of the chat platform, e.g. "twitch" for IRC and EventSub alike
+    pub platform: String,
+    /// `channel_key` of the channel name
+    pub channel: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The platform a channel route is keyed by. Twitch chat, Helix and EventSub
+/// all name the same broadcaster channel, so they share one route.
+pub fn channel_platform(platform: &str) -> String {
+    let platform = platform.trim().to_lowercase();
+    if platform == "twitch" || platform.starts_with("twitch-") {
+        "twitch".to_string()
+    } else {
+        platform
+    }
+}
+
+/// Channel names as routes store them: lowercase, without IRC's leading '#'.
+pub fn channel_key(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// A broadcaster's slice of the server: its own commands, redeems, config and credentials.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Workspace {
+    pub fn is_default(&self) -> bool {
+        self.workspace_id == DEFAULT_WORKSPACE_ID
+    }
+
+    /// Workspace names are lowercase slugs: letters, digits, `-` and `_`.
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c.is_ascii_digit())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_twitch_platforms_share_channel_routes() {
+        assert_eq!(channel_platform("twitch-irc"), "twitch");
+        assert_eq!(channel_platform("Twitch-EventSub"), "twitch");
+        assert_eq!(channel_platform("discord"), "discord");
+        assert_eq!(channel_key(" #Maow "), "maow");
+    }
+}
@@ -1,14 +1,12 @@
 use std::collections::HashMap;
-use std::format;
 use async_trait::async_trait;
 use tokio::sync::mpsc;
-use twilight_model::id::marker::ApplicationMarker;
 use uuid::Uuid;
 use crate::error::Error;
 use crate::models::{Command, CommandUsage, Redeem, RedeemUsage, UserAnalysis};
 use crate::models::analytics::{BotEvent, ChatMessage};
 use crate::models::auth::Platform;
-use crate::models::discord::{DiscordAccountRecord, DiscordChannelRecord, DiscordEmbed, DiscordEventConfigRecord, DiscordGuildRecord, DiscordLiveRoleRecord};
+use crate::models::discord::{DiscordChannelRecord, DiscordEmbed, DiscordEventConfigRecord, DiscordGuildRecord, DiscordLiveRoleRecord};
 use crate::models::drip::DripAvatarSummary;
 use crate::models::platform::{PlatformConfigData, PlatformCredential, PlatformIdentity};
 use crate::models::plugin::StatusData;
@@ -377,7 +375,7 @@ pub trait AiApi: Send + Sync {
     async fn set_system_prompt(&self, prompt: &str) -> Result<(), Error>;
     
     /// Configure an AI provider with the given configuration
-    async fn configure_ai_provider(&self, config: serde_json::Value) -> Result<(), Error> {
+    async fn configure_ai_provider(&self, _config: serde_json::Value) -> Result<(), Error> {
         // Default implementation just returns an error
         Err(Error::Internal("configure_ai_provider not implemented".to_string()))
     }
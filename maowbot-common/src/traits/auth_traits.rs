@@ -28,6 +28,17 @@ pub trait PlatformAuthenticator: Send {
     fn set_is_teammate(&mut self, val: bool);
 
     fn set_is_bot(&mut self, _val: bool) {}
+
+    /// Starts a device-code login (for hosts without a browser or callback port).
+    /// Returns an `AuthenticationPrompt::DeviceCode`.
+    async fn start_device_authentication(&mut self) -> Result<AuthenticationPrompt, Error> {
+        Err(Error::Auth("Device-code login is not supported for this platform".into()))
+    }
+
+    /// Polls a device-code login once. `Ok(None)` means the user hasn't approved it yet.
+    async fn poll_device_authentication(&mut self) -> Result<Option<PlatformCredential>, Error> {
+        Err(Error::Auth("Device-code login is not supported for this platform".into()))
+    }
 }
 
 #[async_trait]
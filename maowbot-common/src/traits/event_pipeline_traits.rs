@@ -6,7 +6,7 @@ use crate::models::event_pipeline::{
     EventPipeline, PipelineFilter, PipelineAction, PipelineExecutionLog,
     PipelineExecutionStatus, PipelineSharedData, EventTypeRegistry, EventHandlerRegistry,
     CreatePipelineRequest, UpdatePipelineRequest, CreateFilterRequest, CreateActionRequest,
-    HandlerType,
+    HandlerType, PipelineThrottle, ThrottleUsage,
 };
 
 /// Repository trait for managing event pipelines
@@ -66,6 +66,8 @@ pub trait PipelineExecutionLogRepository: Send + Sync {
     ) -> Result<Vec<PipelineExecutionLog>, Error>;
     async fn list_recent_executions(&self, limit: i64) -> Result<Vec<PipelineExecutionLog>, Error>;
     async fn cleanup_old_executions(&self, older_than: DateTime<Utc>) -> Result<i64, Error>;
+    /// Executions that failed or timed out since `since`.
+    async fn count_failed_executions_since(&self, since: DateTime<Utc>) -> Result<i64, Error>;
 }
 
 /// Repository trait for managing pipeline shared data
@@ -122,6 +124,28 @@ pub trait EventHandlerRegistryRepository: Send + Sync {
     async fn delete_handler(&self, handler_id: Uuid) -> Result<(), Error>;
 }
 
+/// Persisted rate limits for pipelines and the runs counted against them
+#[async_trait]
+pub trait PipelineThrottleRepository: Send + Sync {
+    async fn get_throttle(&self, pipeline_id: Uuid) -> Result<Option<PipelineThrottle>, Error>;
+    async fn list_throttles(&self) -> Result<Vec<PipelineThrottle>, Error>;
+    async fn set_throttle(&self, throttle: &PipelineThrottle) -> Result<PipelineThrottle, Error>;
+    async fn delete_throttle(&self, pipeline_id: Uuid) -> Result<(), Error>;
+    /// Runs in `scope_key` ("" for the whole pipeline) since `window_since`,
+    /// and the latest run in that scope
+    async fn throttle_usage(
+        &self,
+        pipeline_id: Uuid,
+        scope_key: &str,
+        window_since: DateTime<Utc>,
+    ) -> Result<ThrottleUsage, Error>;
+    async fn record_throttle_hit(&self, pipeline_id: Uuid, scope_key: &str, at: DateTime<Utc>) -> Result<(), Error>;
+    /// Forgets a pipeline's runs from before `older_than`
+    async fn prune_throttle_hits(&self, pipeline_id: Uuid, older_than: DateTime<Utc>) -> Result<i64, Error>;
+    /// Forgets every counted run of a pipeline
+    async fn reset_throttle_hits(&self, pipeline_id: Uuid) -> Result<i64, Error>;
+}
+
 /// Combined repository trait for all event pipeline operations
 #[async_trait]
 pub trait EventPipelineSystemRepository: 
@@ -129,7 +153,8 @@ pub trait EventPipelineSystemRepository:
     PipelineExecutionLogRepository + 
     PipelineSharedDataRepository +
     EventTypeRegistryRepository +
-    EventHandlerRegistryRepository
+    EventHandlerRegistryRepository +
+    PipelineThrottleRepository
 {
     // Additional convenience methods that span multiple repositories
     
@@ -148,7 +173,7 @@ pub trait EventPipelineSystemRepository:
     async fn clone_pipeline(&self, pipeline_id: Uuid, new_name: &str) -> Result<EventPipeline, Error> {
         if let Some((original, filters, actions)) = self.get_pipeline_with_details(pipeline_id).await? {
             // Create new pipeline
-            let mut request = CreatePipelineRequest {
+            let request = CreatePipelineRequest {
                 name: new_name.to_string(),
                 description: original.description.map(|d| format!("Clone of: {}", d)),
                 enabled: false, // Start disabled
@@ -1,8 +1,7 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::error::Error;
-use crate::models::osc_toggle::{OscTrigger, OscToggleState, OscAvatarConfig};
+use crate::models::osc_toggle::{OscTrigger, OscToggleState, OscAvatarConfig, OscChatControl};
 
 #[async_trait]
 pub trait OscToggleRepository: Send + Sync {
@@ -25,4 +24,12 @@ pub trait OscToggleRepository: Send + Sync {
     // OscAvatarConfig methods
     async fn get_avatar_config(&self, avatar_id: &str) -> Result<Option<OscAvatarConfig>, Error>;
     async fn create_or_update_avatar_config(&self, config: OscAvatarConfig) -> Result<OscAvatarConfig, Error>;
-}
\ No newline at end of file
+
+    // OscChatControl methods
+    async fn list_chat_controls(&self) -> Result<Vec<OscChatControl>, Error>;
+    async fn get_chat_control(&self, name: &str) -> Result<Option<OscChatControl>, Error>;
+    /// Creates the control, or replaces the one with the same name.
+    async fn upsert_chat_control(&self, control: OscChatControl) -> Result<OscChatControl, Error>;
+    /// Returns whether a control by that name existed.
+    async fn delete_chat_control(&self, name: &str) -> Result<bool, Error>;
+}
@@ -1,14 +1,30 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Duration, Utc};
-use serde_json::Value;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use sqlx::types::JsonValue;
 use uuid::Uuid;
 use crate::error::Error;
-use crate::models::{Command, CommandUsage, Redeem, RedeemUsage, UserAnalysis};
-use crate::models::discord::{DiscordAccountRecord, DiscordChannelRecord, DiscordGuildRecord, DiscordLiveRoleRecord};
+use crate::models::{ApiToken, AuditLogEntry, AuditLogFilter, Command, CommandStats, CommandUsage, DripAvatar, DripFitParam, Redeem, RedeemUsage, UserAnalysis, Workspace, WorkspaceChannel};
+use crate::models::discord::{DiscordAccountRecord, DiscordChannelRecord, DiscordEventConfigRecord, DiscordGuildRecord, DiscordLiveRoleRecord};
 use crate::models::link_request::LinkRequest;
 use crate::models::platform::{Platform, PlatformConfig, PlatformCredential, PlatformIdentity};
-use crate::models::user::{User, UserAuditLogEntry};
+use crate::models::user::{User, UserAuditLogEntry, UserListQuery};
+use crate::models::user_notes::{ModerationAction, UserNote};
+use crate::models::donation::{Donation, DonorTotal};
+use crate::models::stream_marker::{StreamMarker, VodChapters};
+use crate::models::giveaway::{Giveaway, GiveawayEntry};
+use crate::models::protection::ProtectionIncident;
+use crate::models::moderation_rule::ModerationRule;
+use crate::models::emote_stats::{EmoteDailyCount, EmoteUsage};
+use crate::models::clip::PostedClip;
+use crate::models::localization::{ChannelLanguage, LanguageString};
+use crate::models::redeem_schedule::RedeemSchedule;
+use crate::models::midi_mapping::MidiMapping;
+use crate::models::watchtime::ViewerWatchtime;
+use crate::models::responder::Responder;
+use crate::models::redeem_approval::{ApprovalStatus, RedeemApproval};
+use crate::models::alert_rule::AlertRule;
+use crate::models::milestone::{SubHistory, ViewerMilestone};
+use crate::models::stream_session::{SessionChatter, SessionStats, StreamSession};
 use crate::models::ai::{
     AiProvider, AiCredential, AiModel, AiTrigger, AiMemory, AiConfiguration, 
     AiTriggerWithDetails, AiAgent, AiAction, AiSystemPrompt, AiAgentWithDetails
@@ -192,6 +208,106 @@ pub trait AnalyticsRepo: Send + Sync {
     ) -> Result<u64, Error>;
 }
 
+/// Lets the chat logger write through a shared `Arc<dyn AnalyticsRepo>`.
+#[async_trait]
+impl<T: AnalyticsRepo + ?Sized> AnalyticsRepo for std::sync::Arc<T> {
+    async fn insert_chat_message(&self, msg: &crate::models::analytics::ChatMessage) -> Result<(), Error> {
+        (**self).insert_chat_message(msg).await
+    }
+    async fn insert_chat_messages(&self, msgs: &[crate::models::analytics::ChatMessage]) -> Result<(), Error> {
+        (**self).insert_chat_messages(msgs).await
+    }
+    async fn get_recent_messages(&self, platform: &str, channel: &str, limit: i64) -> Result<Vec<crate::models::analytics::ChatMessage>, Error> {
+        (**self).get_recent_messages(platform, channel, limit).await
+    }
+    async fn insert_chat_session(&self, session: &crate::models::analytics::ChatSession) -> Result<(), Error> {
+        (**self).insert_chat_session(session).await
+    }
+    async fn close_chat_session(&self, session_id: Uuid, left_at: DateTime<Utc>, duration_seconds: i64) -> Result<(), Error> {
+        (**self).close_chat_session(session_id, left_at, duration_seconds).await
+    }
+    async fn insert_bot_event(&self, event: &crate::models::analytics::BotEvent) -> Result<(), Error> {
+        (**self).insert_bot_event(event).await
+    }
+    async fn update_daily_stats(&self, date_str: &str, new_messages: i64, new_visits: i64) -> Result<(), Error> {
+        (**self).update_daily_stats(date_str, new_messages, new_visits).await
+    }
+    async fn get_messages_for_user(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+        maybe_platform: Option<&str>,
+        maybe_channel: Option<&str>,
+        maybe_search: Option<&str>,
+    ) -> Result<Vec<crate::models::analytics::ChatMessage>, Error> {
+        (**self).get_messages_for_user(user_id, limit, offset, maybe_platform, maybe_channel, maybe_search).await
+    }
+    async fn reassign_user_messages(&self, from_user: Uuid, to_user: Uuid) -> Result<u64, Error> {
+        (**self).reassign_user_messages(from_user, to_user).await
+    }
+}
+
+/// Long-term chat message storage with search, separate from the in-memory ChatCache.
+#[async_trait]
+pub trait ChatArchiveRepository: Send + Sync {
+    /// Newest first. Returns the page selected by `limit`/`offset` and the total number of matches.
+    async fn search_messages(
+        &self,
+        query: &crate::models::analytics::ChatSearchQuery
+    ) -> Result<(Vec<crate::models::analytics::ChatMessage>, i64), Error>;
+
+    async fn last_message_for_user(
+        &self,
+        user_id: Uuid,
+        maybe_platform: Option<&str>,
+        maybe_channel: Option<&str>,
+    ) -> Result<Option<crate::models::analytics::ChatMessage>, Error>;
+
+    async fn list_retention_policies(&self) -> Result<Vec<crate::models::analytics::ChatRetentionPolicy>, Error>;
+    async fn set_retention_policy(&self, platform: &str, channel: &str, retention_days: i32) -> Result<(), Error>;
+    /// Returns false if the channel had no policy.
+    async fn delete_retention_policy(&self, platform: &str, channel: &str) -> Result<bool, Error>;
+
+    /// Deletes messages older than their channel's retention (or `default_days`
+    /// for channels without a policy). Returns the number of rows removed.
+    async fn purge_expired_messages(&self, default_days: i64) -> Result<u64, Error>;
+    /// How many messages `purge_expired_messages` would remove right now.
+    async fn count_expired_messages(&self, default_days: i64) -> Result<u64, Error>;
+
+    /// 5-minute message counts in `[since, until)`, ordered by channel then time.
+    /// Buckets without messages are not returned.
+    async fn chat_activity(
+        &self,
+        maybe_platform: Option<&str>,
+        maybe_channel: Option<&str>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<crate::models::analytics::ChatActivityBucket>, Error>;
+
+    /// Distinct users with a message in `[since, until)`.
+    async fn count_chatters(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Result<i64, Error>;
+
+    /// Up to `limit` users with a message in `[since, until)`, in user_id
+    /// order, starting after `after`.
+    async fn chatters(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, Error>;
+
+    /// A user's messages in `[since, until)`, newest first; all of them when `limit` is None.
+    async fn messages_for_user_between(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: Option<i64>,
+    ) -> Result<Vec<crate::models::analytics::ChatMessage>, Error>;
+}
+
 #[async_trait]
 pub trait BotConfigRepository: Send + Sync {
     async fn get_callback_port(&self) -> Result<Option<u16>, Error>;
@@ -221,6 +337,12 @@ pub trait BotConfigRepository: Send + Sync {
         config_value: &str,
         config_meta: Option<JsonValue>
     ) -> Result<(), Error>;
+    /// Stores `config_value` encrypted under `config_key` (tokens read by
+    /// pipeline actions). Secrets are left out of `get_value` and `list_all`.
+    async fn set_secret(&self, config_key: &str, config_value: &str) -> Result<(), Error>;
+    /// The decrypted value stored with `set_secret`, if any.
+    async fn get_secret(&self, config_key: &str) -> Result<Option<String>, Error>;
+    async fn list_secret_keys(&self) -> Result<Vec<String>, Error>;
 }
 
 #[async_trait]
@@ -228,6 +350,8 @@ pub trait CommandUsageRepository: Send + Sync {
     async fn insert_usage(&self, usage: &CommandUsage) -> Result<(), Error>;
     async fn list_usage_for_command(&self, command_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error>;
     async fn list_usage_for_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error>;
+    /// Uses, failures and handler latency percentiles of a command since `since`.
+    async fn command_stats(&self, command_id: Uuid, since: DateTime<Utc>) -> Result<CommandStats, Error>;
 }
 
 #[async_trait]
@@ -253,6 +377,30 @@ pub trait CredentialsRepository: Send + Sync {
     async fn get_broadcaster_credential(&self, platform: &Platform) -> Result<Option<PlatformCredential>, Error>;
     async fn get_bot_credentials(&self, platform: &Platform) -> Result<Option<PlatformCredential>, Error>;
     async fn get_teammate_credentials(&self, platform: &Platform) -> Result<Vec<PlatformCredential>, Error>;
+    /// Moves a credential into another workspace (e.g. after an auth flow stored it
+    /// through the unscoped repository).
+    async fn assign_workspace(&self, credential_id: Uuid, workspace_id: Uuid) -> Result<(), Error>;
+}
+
+/// VRChat avatars, outfits ("fits") and props for the drip commands.
+#[async_trait]
+pub trait DripRepository: Send + Sync {
+    /// The avatar last seen being worn, if any.
+    fn current_avatar(&self) -> Result<Option<DripAvatar>, Error>;
+    /// Marks an avatar as worn, storing it if it's new.
+    async fn set_current_avatar(&self, user_id: Uuid, vrchat_avatar_id: &str, vrchat_avatar_name: &str) -> Result<(), Error>;
+    async fn add_prefix_rule_ignore(&self, avatar_id: &Uuid, prefix: &str) -> Result<(), Error>;
+    async fn add_prefix_rule_strip(&self, avatar_id: &Uuid, prefix: &str) -> Result<(), Error>;
+    async fn update_local_avatar_name(&self, avatar_id: &Uuid, new_name: &str) -> Result<(), Error>;
+    async fn list_avatars(&self) -> Result<Vec<DripAvatar>, Error>;
+    async fn create_fit(&self, avatar_id: &Uuid, fit_name: &str) -> Result<(), Error>;
+    async fn add_fit_param(&self, fit_name: &str, param_name: &str, param_value: &str) -> Result<(), Error>;
+    async fn del_fit_param(&self, fit_name: &str, param_name: &str, param_value: &str) -> Result<(), Error>;
+    async fn get_fit_params(&self, fit_name: &str) -> Result<Vec<DripFitParam>, Error>;
+    async fn is_param_known_for_current_avatar(&self, param_name: String) -> Result<bool, Error>;
+    async fn add_prop_param(&self, prop_name: &str, param_name: &str, param_value: &str) -> Result<(), Error>;
+    async fn del_prop_param(&self, prop_name: &str, param_name: &str, param_value: &str) -> Result<(), Error>;
+    async fn add_prop_timer(&self, prop_name: &str, timer_data: &str) -> Result<(), Error>;
 }
 
 #[async_trait]
@@ -320,6 +468,41 @@ pub trait RedeemRepository: Send + Sync {
     async fn delete_redeem(&self, redeem_id: Uuid) -> Result<(), Error>;
 }
 
+#[async_trait]
+pub trait WorkspaceRepository: Send + Sync {
+    async fn create_workspace(&self, ws: &Workspace) -> Result<(), Error>;
+    async fn get_workspace(&self, workspace_id: Uuid) -> Result<Option<Workspace>, Error>;
+    async fn get_workspace_by_name(&self, name: &str) -> Result<Option<Workspace>, Error>;
+    async fn list_workspaces(&self) -> Result<Vec<Workspace>, Error>;
+    /// Deletes the workspace with its commands, redeems, config and channel
+    /// routes. Fails while it still has credentials: move or remove those first.
+    async fn delete_workspace(&self, workspace_id: Uuid) -> Result<(), Error>;
+
+    /// Routes `channel` on `platform` to `workspace_id`, replacing any earlier route.
+    /// Both are normalized with `channel_platform` and `channel_key`.
+    async fn assign_channel(&self, workspace_id: Uuid, platform: &str, channel: &str) -> Result<(), Error>;
+    /// Sends the channel back to the default workspace. Returns whether it had a route.
+    async fn unassign_channel(&self, platform: &str, channel: &str) -> Result<bool, Error>;
+    /// Every channel route, by platform and channel.
+    async fn list_channels(&self) -> Result<Vec<WorkspaceChannel>, Error>;
+}
+
+#[async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    async fn create_token(&self, token: &ApiToken) -> Result<(), Error>;
+    async fn get_token_by_name(&self, name: &str) -> Result<Option<ApiToken>, Error>;
+    /// All tokens, including revoked ones.
+    async fn list_tokens(&self) -> Result<Vec<ApiToken>, Error>;
+    async fn revoke_token(&self, token_id: Uuid) -> Result<(), Error>;
+    /// Replaces the token's hash and un-revokes it (used to re-issue the local admin token).
+    async fn reset_token_hash(&self, token_id: Uuid, token_hash: &str) -> Result<(), Error>;
+    async fn touch_token(&self, token_id: Uuid, used_at: DateTime<Utc>) -> Result<(), Error>;
+
+    async fn insert_audit_entry(&self, entry: &AuditLogEntry) -> Result<(), Error>;
+    /// Most recent entries first.
+    async fn list_audit_entries(&self, filter: &AuditLogFilter, limit: i64) -> Result<Vec<AuditLogEntry>, Error>;
+}
+
 #[async_trait::async_trait]
 pub trait UserRepo {
     async fn create(&self, user: &User) -> Result<(), Error>;
@@ -328,6 +511,11 @@ pub trait UserRepo {
     async fn update(&self, user: &User) -> Result<(), Error>;
     async fn delete(&self, id: Uuid) -> Result<(), Error>;
     async fn list_all(&self) -> Result<Vec<User>, Error>;
+    /// One page of users matching `query`, with the number of matches overall.
+    async fn list_page(&self, query: &UserListQuery) -> Result<(Vec<User>, i64), Error>;
+    /// Moves identities, usage, notes and audit history of the duplicates onto
+    /// the primary user, then deletes the duplicates.
+    async fn merge_users(&self, primary_user_id: Uuid, duplicate_user_ids: Vec<Uuid>) -> Result<(), Error>;
 }
 
 #[async_trait]
@@ -335,6 +523,25 @@ pub trait UserAnalysisRepository: Send + Sync {
     async fn create_analysis(&self, analysis: &UserAnalysis) -> Result<(), Error>;
     async fn get_analysis(&self, user_id: Uuid) -> Result<Option<UserAnalysis>, Error>;
     async fn update_analysis(&self, analysis: &UserAnalysis) -> Result<(), Error>;
+    /// Records `scores` (the four scores and notes) as the user's `year_month` ("YYYY-MM") history entry.
+    async fn insert_history(&self, year_month: &str, scores: &UserAnalysis) -> Result<(), Error>;
+}
+
+/// Lets `ChatCache<Arc<dyn UserAnalysisRepository>>` share one repository.
+#[async_trait]
+impl<T: UserAnalysisRepository + ?Sized> UserAnalysisRepository for std::sync::Arc<T> {
+    async fn create_analysis(&self, analysis: &UserAnalysis) -> Result<(), Error> {
+        (**self).create_analysis(analysis).await
+    }
+    async fn get_analysis(&self, user_id: Uuid) -> Result<Option<UserAnalysis>, Error> {
+        (**self).get_analysis(user_id).await
+    }
+    async fn update_analysis(&self, analysis: &UserAnalysis) -> Result<(), Error> {
+        (**self).update_analysis(analysis).await
+    }
+    async fn insert_history(&self, year_month: &str, scores: &UserAnalysis) -> Result<(), Error> {
+        (**self).insert_history(year_month, scores).await
+    }
 }
 
 #[async_trait]
@@ -345,7 +552,222 @@ pub trait UserAuditLogRepository {
 }
 
 #[async_trait]
-pub trait DiscordRepository {
+pub trait UserNotesRepository: Send + Sync {
+    async fn add_note(&self, note: &UserNote) -> Result<(), Error>;
+    /// Newest first.
+    async fn list_notes(&self, user_id: Uuid, limit: i64) -> Result<Vec<UserNote>, Error>;
+    /// Returns false if the note did not exist.
+    async fn delete_note(&self, note_id: Uuid) -> Result<bool, Error>;
+
+    async fn record_action(&self, action: &ModerationAction) -> Result<(), Error>;
+    /// Newest first, across all platforms.
+    async fn list_actions(&self, user_id: Uuid, limit: i64) -> Result<Vec<ModerationAction>, Error>;
+    /// Warnings, timeouts and bans on the escalation ladder (those with a
+    /// category) since `since`, leaving out ones a later pardon in the same
+    /// category cleared. Newest first.
+    async fn list_offenses(&self, user_id: Uuid, since: Option<DateTime<Utc>>) -> Result<Vec<ModerationAction>, Error>;
+}
+
+#[async_trait]
+pub trait DonationRepository: Send + Sync {
+    /// Returns false if a donation with the same source and external id was already stored.
+    async fn record_donation(&self, donation: &Donation) -> Result<bool, Error>;
+    /// Newest first.
+    async fn list_recent(&self, limit: i64) -> Result<Vec<Donation>, Error>;
+    /// Biggest donors by total in the base currency, optionally only since `since`.
+    /// Donations without a converted amount are left out.
+    async fn top_donors(&self, since: Option<DateTime<Utc>>, limit: i64) -> Result<Vec<DonorTotal>, Error>;
+}
+
+#[async_trait]
+pub trait StreamMarkerRepository: Send + Sync {
+    async fn insert_marker(&self, marker: &StreamMarker) -> Result<(), Error>;
+    /// Markers of one broadcast, in stream order.
+    async fn list_markers(&self, stream_id: &str) -> Result<Vec<StreamMarker>, Error>;
+    /// Inserts or replaces the chapter list for `chapters.stream_id`.
+    async fn save_vod_chapters(&self, chapters: &VodChapters) -> Result<(), Error>;
+    /// Looks up by stream id or VOD id.
+    async fn get_vod_chapters(&self, id: &str) -> Result<Option<VodChapters>, Error>;
+    /// Newest first.
+    async fn list_vod_chapters(&self, limit: i64) -> Result<Vec<VodChapters>, Error>;
+}
+
+#[async_trait]
+pub trait GiveawayRepository: Send + Sync {
+    async fn create_giveaway(&self, giveaway: &Giveaway) -> Result<(), Error>;
+    async fn update_giveaway(&self, giveaway: &Giveaway) -> Result<(), Error>;
+    async fn get_giveaway(&self, giveaway_id: Uuid) -> Result<Option<Giveaway>, Error>;
+    /// Giveaways still accepting entries.
+    async fn list_open_giveaways(&self) -> Result<Vec<Giveaway>, Error>;
+    /// Newest first.
+    async fn list_giveaways(&self, limit: i64) -> Result<Vec<Giveaway>, Error>;
+    /// Returns false if the user had already entered.
+    async fn add_entry(&self, entry: &GiveawayEntry) -> Result<bool, Error>;
+    /// In entry order.
+    async fn list_entries(&self, giveaway_id: Uuid) -> Result<Vec<GiveawayEntry>, Error>;
+    async fn update_entry(&self, entry: &GiveawayEntry) -> Result<(), Error>;
+    /// Whether the user won a giveaway (and was not re-rolled) since `since`.
+    async fn has_won_since(&self, user_id: Uuid, since: DateTime<Utc>) -> Result<bool, Error>;
+}
+
+#[async_trait]
+pub trait ProtectionIncidentRepository: Send + Sync {
+    async fn create_incident(&self, incident: &ProtectionIncident) -> Result<(), Error>;
+    /// Stores the actions, captured messages and end of an incident.
+    async fn update_incident(&self, incident: &ProtectionIncident) -> Result<(), Error>;
+    async fn get_incident(&self, incident_id: Uuid) -> Result<Option<ProtectionIncident>, Error>;
+    /// Newest first, without captured messages.
+    async fn list_incidents(&self, limit: i64) -> Result<Vec<ProtectionIncident>, Error>;
+}
+
+#[async_trait]
+pub trait ModerationRuleRepository: Send + Sync {
+    async fn create_rule(&self, rule: &ModerationRule) -> Result<(), Error>;
+    /// Saves everything but the hit counter.
+    async fn update_rule(&self, rule: &ModerationRule) -> Result<(), Error>;
+    async fn delete_rule(&self, rule_id: Uuid) -> Result<(), Error>;
+    async fn get_rule_by_name(&self, name: &str) -> Result<Option<ModerationRule>, Error>;
+    /// In creation order.
+    async fn list_rules(&self) -> Result<Vec<ModerationRule>, Error>;
+    async fn record_hit(&self, rule_id: Uuid, at: DateTime<Utc>) -> Result<(), Error>;
+    async fn reset_hits(&self, rule_id: Uuid) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait EmoteStatsRepository: Send + Sync {
+    /// Adds each count to the stored total for its channel, day and emote.
+    async fn add_daily_counts(&self, counts: &[EmoteDailyCount]) -> Result<(), Error>;
+    /// Most used emotes of a channel from `since` (inclusive) on, most used first.
+    async fn top_emotes(&self, channel: &str, since: NaiveDate, limit: i64) -> Result<Vec<EmoteUsage>, Error>;
+}
+
+#[async_trait]
+pub trait ClipRepository: Send + Sync {
+    /// Records a posted clip; false if it was already recorded.
+    async fn insert_clip(&self, clip: &PostedClip) -> Result<bool, Error>;
+    /// The ids among `clip_ids` that have already been posted.
+    async fn posted_clip_ids(&self, clip_ids: &[String]) -> Result<Vec<String>, Error>;
+    /// Newest first.
+    async fn list_clips(&self, limit: i64) -> Result<Vec<PostedClip>, Error>;
+}
+
+#[async_trait]
+pub trait RedeemScheduleRepository: Send + Sync {
+    async fn create_schedule(&self, schedule: &RedeemSchedule) -> Result<(), Error>;
+    async fn update_schedule(&self, schedule: &RedeemSchedule) -> Result<(), Error>;
+    async fn delete_schedule(&self, schedule_id: Uuid) -> Result<(), Error>;
+    /// Every schedule, by redeem and then priority.
+    async fn list_schedules(&self) -> Result<Vec<RedeemSchedule>, Error>;
+}
+
+#[async_trait]
+pub trait MidiMappingRepository: Send + Sync {
+    async fn create_mapping(&self, mapping: &MidiMapping) -> Result<(), Error>;
+    async fn update_mapping(&self, mapping: &MidiMapping) -> Result<(), Error>;
+    async fn delete_mapping(&self, mapping_id: Uuid) -> Result<(), Error>;
+    async fn get_mapping_by_name(&self, name: &str) -> Result<Option<MidiMapping>, Error>;
+    /// By trigger kind, channel and number.
+    async fn list_mappings(&self) -> Result<Vec<MidiMapping>, Error>;
+}
+
+#[async_trait]
+pub trait WatchtimeRepository: Send + Sync {
+    /// Adds each entry's `watch_seconds` to the viewer's total in its channel,
+    /// updating their login, display name and `last_seen`.
+    async fn add_watchtime(&self, entries: &[ViewerWatchtime]) -> Result<(), Error>;
+    /// `login` is matched case-insensitively.
+    async fn get_watchtime(&self, channel: &str, login: &str) -> Result<Option<ViewerWatchtime>, Error>;
+}
+
+#[async_trait]
+pub trait ResponderRepository: Send + Sync {
+    async fn create_responder(&self, responder: &Responder) -> Result<(), Error>;
+    /// Saves everything but the hit counter.
+    async fn update_responder(&self, responder: &Responder) -> Result<(), Error>;
+    async fn delete_responder(&self, responder_id: Uuid) -> Result<(), Error>;
+    async fn get_responder_by_name(&self, name: &str) -> Result<Option<Responder>, Error>;
+    /// In creation order.
+    async fn list_responders(&self) -> Result<Vec<Responder>, Error>;
+    async fn record_hit(&self, responder_id: Uuid, at: DateTime<Utc>) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait RedeemApprovalRepository: Send + Sync {
+    async fn create_approval(&self, approval: &RedeemApproval) -> Result<(), Error>;
+    async fn get_approval(&self, approval_id: Uuid) -> Result<Option<RedeemApproval>, Error>;
+    /// Oldest first.
+    async fn list_pending(&self) -> Result<Vec<RedeemApproval>, Error>;
+    /// Moves a pending approval to `status`. False if it was already decided,
+    /// so two moderators can't both act on one redemption.
+    async fn decide(
+        &self,
+        approval_id: Uuid,
+        status: ApprovalStatus,
+        decided_by: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<bool, Error>;
+    /// Pending approvals requested before `before`.
+    async fn list_pending_before(&self, before: DateTime<Utc>) -> Result<Vec<RedeemApproval>, Error>;
+}
+
+#[async_trait]
+pub trait AlertRuleRepository: Send + Sync {
+    async fn create_rule(&self, rule: &AlertRule) -> Result<(), Error>;
+    async fn update_rule(&self, rule: &AlertRule) -> Result<(), Error>;
+    async fn delete_rule(&self, rule_id: Uuid) -> Result<(), Error>;
+    async fn get_rule_by_name(&self, name: &str) -> Result<Option<AlertRule>, Error>;
+    /// In creation order.
+    async fn list_rules(&self) -> Result<Vec<AlertRule>, Error>;
+}
+
+#[async_trait]
+pub trait MilestoneRepository: Send + Sync {
+    /// Stores a celebrated milestone; false if the viewer already had it.
+    async fn record_milestone(&self, milestone: &ViewerMilestone) -> Result<bool, Error>;
+    /// Newest first.
+    async fn list_milestones(&self, limit: i64) -> Result<Vec<ViewerMilestone>, Error>;
+    async fn get_sub_history(&self, channel: &str, twitch_user_id: &str) -> Result<Option<SubHistory>, Error>;
+    async fn upsert_sub_history(&self, history: &SubHistory) -> Result<(), Error>;
+    async fn get_follow_date(&self, channel: &str, twitch_user_id: &str) -> Result<Option<DateTime<Utc>>, Error>;
+    async fn upsert_follow_date(&self, channel: &str, twitch_user_id: &str, followed_at: DateTime<Utc>) -> Result<(), Error>;
+}
+
+#[async_trait]
+pub trait StreamSessionRepository: Send + Sync {
+    async fn create_session(&self, session: &StreamSession) -> Result<(), Error>;
+    async fn get_session(&self, session_id: Uuid) -> Result<Option<StreamSession>, Error>;
+    async fn get_session_by_stream_id(&self, stream_id: &str) -> Result<Option<StreamSession>, Error>;
+    /// The newest session of the channel that has not ended.
+    async fn get_open_session(&self, channel: &str) -> Result<Option<StreamSession>, Error>;
+    /// The newest session of any channel, live or not.
+    async fn get_latest_session(&self) -> Result<Option<StreamSession>, Error>;
+    /// Newest first.
+    async fn list_sessions(&self, limit: i64) -> Result<Vec<StreamSession>, Error>;
+    async fn update_details(&self, session_id: Uuid, title: &str, category: &str) -> Result<(), Error>;
+    async fn save_stats(&self, session_id: Uuid, stats: &SessionStats) -> Result<(), Error>;
+    async fn close_session(&self, session_id: Uuid, ended_at: DateTime<Utc>) -> Result<(), Error>;
+    /// Adds (twitch_user_id, display_name, messages) to each chatter's count.
+    async fn add_chatter_messages(&self, session_id: Uuid, chatters: &[(String, String, i64)]) -> Result<(), Error>;
+    async fn count_chatters(&self, session_id: Uuid) -> Result<i64, Error>;
+    /// Most messages first.
+    async fn top_chatters(&self, session_id: Uuid, limit: i64) -> Result<Vec<SessionChatter>, Error>;
+}
+
+#[async_trait]
+pub trait LocalizationRepository: Send + Sync {
+    async fn list_strings(&self) -> Result<Vec<LanguageString>, Error>;
+    /// Inserts or replaces the templates of one language in a single transaction.
+    async fn upsert_strings(&self, language: &str, strings: &[(String, String)]) -> Result<(), Error>;
+    /// False if there was no such string.
+    async fn delete_string(&self, language: &str, key: &str) -> Result<bool, Error>;
+    async fn list_channel_languages(&self) -> Result<Vec<ChannelLanguage>, Error>;
+    async fn set_channel_language(&self, channel: &ChannelLanguage) -> Result<(), Error>;
+    /// False if the channel had no language set.
+    async fn clear_channel_language(&self, platform: &str, channel: &str) -> Result<bool, Error>;
+}
+
+#[async_trait]
+pub trait DiscordRepository: Send + Sync {
     // Existing methods for guilds/channels:
     async fn upsert_guild(&self, account_name: &str, guild_id: &str, guild_name: &str) -> Result<(), Error>;
     async fn list_guilds_for_account(&self, account_name: &str) -> Result<Vec<DiscordGuildRecord>, Error>;
@@ -380,6 +802,26 @@ pub trait DiscordRepository {
     async fn get_live_role(&self, guild_id: &str) -> Result<Option<DiscordLiveRoleRecord>, Error>;
     async fn delete_live_role(&self, guild_id: &str) -> Result<(), Error>;
     async fn list_live_roles(&self) -> Result<Vec<DiscordLiveRoleRecord>, Error>;
+
+    // Where bot events (stream.online, discord.message, ...) get posted
+    async fn get_event_config_by_name(&self, event_name: &str) -> Result<Option<DiscordEventConfigRecord>, Error>;
+    async fn list_event_configs(&self) -> Result<Vec<DiscordEventConfigRecord>, Error>;
+    async fn insert_event_config_multi(
+        &self,
+        event_name: &str,
+        guild_id: &str,
+        channel_id: &str,
+        respond_with_credential: Option<Uuid>,
+    ) -> Result<(), Error>;
+    async fn remove_event_config_multi(
+        &self,
+        event_name: &str,
+        guild_id: &str,
+        channel_id: &str,
+        respond_with_credential: Option<Uuid>,
+    ) -> Result<(), Error>;
+    async fn add_event_config_role(&self, event_name: &str, role_id: &str) -> Result<(), Error>;
+    async fn remove_event_config_role(&self, event_name: &str, role_id: &str) -> Result<(), Error>;
 }
 
 /// Repository trait for managing OBS instances
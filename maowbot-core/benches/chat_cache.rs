@@ -0,0 +1,141 @@
+// Benchmarks for the sharded chat cache: writes into full rings (steady state,
+// every insert evicts), writes spread over channels and from several threads,
+// and the two read paths.
+
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use uuid::Uuid;
+
+use maowbot_common::models::cache::{CacheConfig, CachedMessage, TrimPolicy};
+use maowbot_common::models::user_analysis::UserAnalysis;
+use maowbot_core::cache::message_cache::ChatCache;
+use maowbot_core::repositories::postgres::user_analysis::UserAnalysisRepository;
+use maowbot_core::Error;
+
+const RING_SIZE: usize = 500;
+const CHANNELS: usize = 64;
+
+struct NoAnalysis;
+
+#[async_trait]
+impl UserAnalysisRepository for NoAnalysis {
+    async fn create_analysis(&self, _: &UserAnalysis) -> Result<(), Error> { Ok(()) }
+    async fn get_analysis(&self, _: Uuid) -> Result<Option<UserAnalysis>, Error> { Ok(None) }
+    async fn update_analysis(&self, _: &UserAnalysis) -> Result<(), Error> { Ok(()) }
+    async fn insert_history(&self, _: &str, _: &UserAnalysis) -> Result<(), Error> { Ok(()) }
+}
+
+fn cache() -> ChatCache<NoAnalysis> {
+    ChatCache::new(NoAnalysis, CacheConfig::new(TrimPolicy {
+        max_age_seconds: None,
+        spam_score_cutoff: None,
+        max_total_messages: None,
+        max_messages_per_channel: Some(RING_SIZE),
+        max_messages_per_user: None,
+        min_quality_score: None,
+    }))
+}
+
+fn message(channel: usize, n: usize) -> CachedMessage {
+    CachedMessage {
+        platform: "twitch-irc".to_string(),
+        channel: format!("#channel{}", channel),
+        user_id: Uuid::nil(),
+        user_name: format!("viewer{}", n % 100),
+        text: "hello chat, this is a fairly ordinary message".to_string(),
+        timestamp: Utc::now(),
+        token_count: 8,
+        user_roles: Vec::new(),
+    }
+}
+
+/// A cache with every channel's ring full.
+fn full_cache() -> ChatCache<NoAnalysis> {
+    let cache = cache();
+    for channel in 0..CHANNELS {
+        for n in 0..RING_SIZE {
+            cache.add_message(message(channel, n));
+        }
+    }
+    cache
+}
+
+fn add_message(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chat_cache/add_message");
+    group.throughput(Throughput::Elements(1));
+
+    let one_channel = cache();
+    let mut n = 0;
+    group.bench_function("one_channel", |b| {
+        b.iter(|| {
+            n += 1;
+            one_channel.add_message(message(0, n));
+        })
+    });
+
+    let many_channels = full_cache();
+    let mut n = 0;
+    group.bench_function("many_channels", |b| {
+        b.iter(|| {
+            n += 1;
+            many_channels.add_message(message(n % CHANNELS, n));
+        })
+    });
+    group.finish();
+}
+
+/// Writers on separate threads, each with its own channel, or all in one.
+fn concurrent_writers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chat_cache/concurrent_add");
+    for threads in [1usize, 4, 8] {
+        for shared_channel in [false, true] {
+            let id = BenchmarkId::new(if shared_channel { "shared_channel" } else { "own_channel" }, threads);
+            let cache = Arc::new(cache());
+            group.throughput(Throughput::Elements(threads as u64));
+            group.bench_function(id, |b| {
+                b.iter_custom(|iters| {
+                    let started = Instant::now();
+                    std::thread::scope(|scope| {
+                        for t in 0..threads {
+                            let cache = cache.clone();
+                            let channel = if shared_channel { 0 } else { t };
+                            scope.spawn(move || {
+                                for n in 0..iters as usize {
+                                    cache.add_message(message(channel, n));
+                                }
+                            });
+                        }
+                    });
+                    started.elapsed()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn reads(c: &mut Criterion) {
+    let cache = full_cache();
+    let since = Utc::now() - Duration::minutes(5);
+    let mut group = c.benchmark_group("chat_cache/read");
+    group.bench_function("channel_messages", |b| {
+        b.iter(|| cache.get_channel_messages("twitch-irc", "#channel7", since, None))
+    });
+    group.bench_function("recent_messages_token_limit", |b| {
+        b.iter(|| cache.get_recent_messages(since, Some(2000), None))
+    });
+    group.bench_function("recent_messages_one_user", |b| {
+        b.iter(|| cache.get_recent_messages(since, None, Some("viewer42")))
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().measurement_time(StdDuration::from_secs(5));
+    targets = add_message, concurrent_writers, reads
+}
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+// Benchmarks for event bus fan-out: one publish awaits every subscriber's
+// channel, so the cost grows with the number of subscribers. Each subscriber
+// here is drained by its own task, like the services do.
+
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+
+use maowbot_core::eventbus::{BotEvent, EventBus};
+
+fn chat_event(n: u64) -> BotEvent {
+    let mut metadata = serde_json::Map::new();
+    metadata.insert("platform_user_id".into(), "12345".into());
+    metadata.insert("username".into(), "viewer".into());
+    BotEvent::ChatMessage {
+        platform: "twitch-irc".to_string(),
+        channel: "#maowbot".to_string(),
+        user: "00000000-0000-0000-0000-000000000000".to_string(),
+        text: format!("hello chat {}", n),
+        timestamp: Utc::now(),
+        metadata,
+    }
+}
+
+/// A bus with `subscribers` receivers, each drained in the background.
+fn bus_with_subscribers(rt: &Runtime, subscribers: usize) -> Arc<EventBus> {
+    let bus = Arc::new(EventBus::new());
+    rt.block_on(async {
+        for _ in 0..subscribers {
+            let mut rx = bus.subscribe(None).await;
+            tokio::spawn(async move { while rx.recv().await.is_some() {} });
+        }
+    });
+    bus
+}
+
+fn publish(c: &mut Criterion) {
+    let rt = Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("event_bus/publish");
+    group.throughput(Throughput::Elements(1));
+    for subscribers in [1usize, 8, 32] {
+        let bus = bus_with_subscribers(&rt, subscribers);
+        let mut n = 0;
+        group.bench_with_input(BenchmarkId::new("chat_message", subscribers), &subscribers, |b, _| {
+            b.to_async(&rt).iter(|| {
+                n += 1;
+                let event = chat_event(n);
+                let bus = bus.clone();
+                async move { bus.publish(event).await }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Several producers publishing at once, as platform runtimes do.
+fn concurrent_publishers(c: &mut Criterion) {
+    let rt = Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("event_bus/concurrent_publish");
+    let subscribers = 8;
+    for publishers in [1usize, 4, 16] {
+        let bus = bus_with_subscribers(&rt, subscribers);
+        group.throughput(Throughput::Elements(publishers as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(publishers), &publishers, |b, &publishers| {
+            b.to_async(&rt).iter(|| {
+                let bus = bus.clone();
+                async move {
+                    let tasks: Vec<_> = (0..publishers as u64)
+                        .map(|n| {
+                            let bus = bus.clone();
+                            tokio::spawn(async move { bus.publish(chat_event(n)).await })
+                        })
+                        .collect();
+                    for task in tasks {
+                        let _ = task.await;
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(5));
+    targets = publish, concurrent_publishers
+}
+criterion_main!(benches);
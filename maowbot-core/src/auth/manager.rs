@@ -4,7 +4,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 use maowbot_common::error::Error;
 
-use maowbot_common::traits::auth_traits::PlatformAuthenticator;
+use maowbot_common::traits::auth_traits::{AuthenticationHandler, PlatformAuthenticator};
 use maowbot_common::models::platform::{Platform, PlatformCredential};
 use maowbot_common::traits::repository_traits::{BotConfigRepository, CredentialsRepository};
 use crate::auth::{AuthenticationPrompt, AuthenticationResponse};
@@ -15,6 +15,7 @@ use crate::platforms::twitch_eventsub::TwitchEventSubAuthenticator;
 use crate::platforms::twitch::auth::TwitchAuthenticator;
 use crate::platforms::vrchat::auth::VRChatAuthenticator;
 use crate::platforms::twitch_irc::auth::TwitchIrcAuthenticator;
+use crate::platforms::kick::auth::KickAuthenticator;
 
 pub struct AuthManager {
     pub credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
@@ -71,6 +72,7 @@ impl AuthManager {
             Platform::VRChat => Box::new(VRChatAuthenticator::new()),
             Platform::TwitchIRC => Box::new(TwitchIrcAuthenticator::new(client_id, client_secret)),
             Platform::TwitchEventSub => Box::new(TwitchEventSubAuthenticator::new(client_id, client_secret)),
+            Platform::Kick => Box::new(KickAuthenticator::new(client_id, client_secret)),
             Platform::OBS => {
                 // OBS doesn't use OAuth, so we can't create an authenticator this way
                 return Err(Error::Platform("OBS does not use OAuth authentication".into()));
@@ -91,6 +93,59 @@ impl AuthManager {
         platform: Platform,
         is_bot: bool,
     ) -> Result<String, Error> {
+        self.create_authenticator(&platform, is_bot).await?;
+
+        // now do `start_authentication` in a short scope
+        let prompt = {
+            let auth = self.authenticators.get_mut(&platform).unwrap();
+            auth.start_authentication().await?
+        };
+        match prompt {
+            AuthenticationPrompt::Browser { url } => Ok(url),
+            AuthenticationPrompt::Code { message } => Err(Error::Auth(message)),
+            AuthenticationPrompt::ApiKey { message } => Ok(format!("(API key) {message}")),
+            // CHANGE HERE to match the TUI check for "MultipleKeys"
+            AuthenticationPrompt::MultipleKeys { .. } => Ok("(MultipleKeys) handle in TUI".into()),
+            AuthenticationPrompt::TwoFactor { message } => Ok(format!("(2FA) {message}")),
+            AuthenticationPrompt::DeviceCode { user_code, verification_uri, .. } => {
+                Ok(format!("(DeviceCode) enter {user_code} at {verification_uri}"))
+            }
+            AuthenticationPrompt::None => Ok("(No prompt needed)".into()),
+        }
+    }
+
+    /// Starts a device-code login (no browser/callback needed on this host).
+    /// The caller shows the code and URL, then calls `poll_device_auth_flow`.
+    pub async fn begin_device_auth_flow(
+        &mut self,
+        platform: Platform,
+        is_bot: bool,
+    ) -> Result<AuthenticationPrompt, Error> {
+        self.create_authenticator(&platform, is_bot).await?;
+        let auth = self.authenticators.get_mut(&platform).unwrap();
+        auth.start_device_authentication().await
+    }
+
+    /// Polls a device-code login once. Returns `Ok(None)` until the user approves it;
+    /// on success the credential is stored for `user_id`.
+    pub async fn poll_device_auth_flow(
+        &mut self,
+        platform: Platform,
+        user_id: &Uuid,
+    ) -> Result<Option<PlatformCredential>, Error> {
+        let Some(auth) = self.authenticators.get_mut(&platform) else {
+            return Err(Error::Platform(format!("No authenticator for {platform:?}")));
+        };
+        let Some(mut cred) = auth.poll_device_authentication().await? else {
+            return Ok(None);
+        };
+        cred.user_id = *user_id;
+        self.credentials_repo.store_credentials(&cred).await?;
+        Ok(Some(cred))
+    }
+
+    /// Creates a fresh authenticator for `platform` from its platform_config row.
+    async fn create_authenticator(&mut self, platform: &Platform, is_bot: bool) -> Result<(), Error> {
         // fetch config from DB
         let platform_str = platform.to_string();
         let maybe_conf = self.platform_config_repo.get_by_platform(&platform_str).await?;
@@ -110,6 +165,7 @@ impl AuthManager {
             Platform::VRChat => Box::new(VRChatAuthenticator::new()),
             Platform::TwitchIRC => Box::new(TwitchIrcAuthenticator::new(client_id, client_secret)),
             Platform::TwitchEventSub => Box::new(TwitchEventSubAuthenticator::new(client_id, client_secret)),
+            Platform::Kick => Box::new(KickAuthenticator::new(client_id, client_secret)),
             Platform::OBS => {
                 // OBS doesn't use OAuth, so we can't create an authenticator this way
                 return Err(Error::Platform("OBS does not use OAuth authentication".into()));
@@ -120,21 +176,7 @@ impl AuthManager {
 
         // store in our HashMap
         self.authenticators.insert(platform.clone(), authenticator);
-
-        // now do `start_authentication` in a short scope
-        let prompt = {
-            let auth = self.authenticators.get_mut(&platform).unwrap();
-            auth.start_authentication().await?
-        };
-        match prompt {
-            AuthenticationPrompt::Browser { url } => Ok(url),
-            AuthenticationPrompt::Code { message } => Err(Error::Auth(message)),
-            AuthenticationPrompt::ApiKey { message } => Ok(format!("(API key) {message}")),
-            // CHANGE HERE to match the TUI check for "MultipleKeys"
-            AuthenticationPrompt::MultipleKeys { .. } => Ok("(MultipleKeys) handle in TUI".into()),
-            AuthenticationPrompt::TwoFactor { message } => Ok(format!("(2FA) {message}")),
-            AuthenticationPrompt::None => Ok("(No prompt needed)".into()),
-        }
+        Ok(())
     }
 
     pub async fn complete_auth_flow_for_user(
@@ -161,8 +203,11 @@ impl AuthManager {
         &mut self,
         platform: Platform,
         user_id: &Uuid,
-        keys: HashMap<String, String>,
+        mut keys: HashMap<String, String>,
     ) -> Result<PlatformCredential, Error> {
+        for (k, v) in self.remembered_keys(&platform, user_id).await? {
+            keys.entry(k).or_insert(v);
+        }
         let Some(auth) = self.authenticators.get_mut(&platform) else {
             return Err(Error::Platform(format!("No authenticator for {platform:?}")));
         };
@@ -194,6 +239,77 @@ impl AuthManager {
         Ok(cred)
     }
 
+    /// Logs `user_id` back in to `platform` when its session is gone, asking
+    /// `handler` for the login fields and, if the platform wants one, a 2FA code.
+    /// The new credential keeps the old one's role flags.
+    pub async fn reauthenticate(
+        &mut self,
+        platform: Platform,
+        user_id: &Uuid,
+        handler: &dyn AuthenticationHandler,
+    ) -> Result<PlatformCredential, Error> {
+        let old_cred = self.credentials_repo.get_credentials(&platform, *user_id).await?
+            .ok_or_else(|| Error::Auth("No credentials found".into()))?;
+        self.create_authenticator(&platform, old_cred.is_bot).await?;
+
+        let prompt = {
+            let auth = self.authenticators.get_mut(&platform).unwrap();
+            auth.set_is_broadcaster(old_cred.is_broadcaster);
+            auth.set_is_teammate(old_cred.is_teammate);
+            auth.start_authentication().await?
+        };
+        let response = match handler.handle_prompt(prompt).await? {
+            AuthenticationResponse::MultipleKeys(mut keys) => {
+                if let Some(name) = old_cred.platform_id.clone() {
+                    keys.entry("username".into()).or_insert(name);
+                }
+                for (k, v) in self.remembered_keys(&platform, user_id).await? {
+                    keys.entry(k).or_insert(v);
+                }
+                AuthenticationResponse::MultipleKeys(keys)
+            }
+            other => other,
+        };
+
+        let auth = self.authenticators.get_mut(&platform).unwrap();
+        let mut cred = match auth.complete_authentication(response).await {
+            Err(Error::Auth(msg)) if msg.contains("__2FA_PROMPT__") => {
+                let code_prompt = AuthenticationPrompt::TwoFactor {
+                    message: format!("Enter the 2FA code for {}:", old_cred.user_name),
+                };
+                let code = handler.handle_prompt(code_prompt).await?;
+                auth.complete_authentication(code).await?
+            }
+            other => other?,
+        };
+
+        cred.credential_id = old_cred.credential_id;
+        cred.user_id = *user_id;
+        cred.created_at = old_cred.created_at;
+        self.credentials_repo.store_credentials(&cred).await?;
+        Ok(cred)
+    }
+
+    /// Login fields worth carrying over from a stored credential, so a re-login
+    /// skips steps the platform still trusts (VRChat's remembered 2FA cookie).
+    async fn remembered_keys(
+        &self,
+        platform: &Platform,
+        user_id: &Uuid,
+    ) -> Result<HashMap<String, String>, Error> {
+        let mut keys = HashMap::new();
+        if *platform != Platform::VRChat {
+            return Ok(keys);
+        }
+        let cookie = self.credentials_repo.get_credentials(platform, *user_id).await?
+            .and_then(|c| c.additional_data)
+            .and_then(|d| d["two_factor_cookie"].as_str().map(str::to_string));
+        if let Some(cookie) = cookie {
+            keys.insert("two_factor_cookie".to_string(), cookie);
+        }
+        Ok(keys)
+    }
+
     // --------------------------
     // Revoke / Refresh
     // --------------------------
@@ -9,13 +9,7 @@ use maowbot_common::models::platform::{ Platform, PlatformIdentity};
 use maowbot_common::models::user_analysis::UserAnalysis;
 pub(crate) use maowbot_common::traits::auth_traits::UserManager;
 
-use crate::repositories::postgres::{
-    user::UserRepository,
-    platform_identity::PlatformIdentityRepository,
-    user_analysis::{UserAnalysisRepository, PostgresUserAnalysisRepository},
-};
-use crate::repositories::postgres::platform_identity::PlatformIdentityRepo;
-use crate::repositories::postgres::user::UserRepo;
+use maowbot_common::traits::repository_traits::{PlatformIdentityRepo, UserAnalysisRepository, UserRepo};
 
 #[derive(Debug, Clone)]
 pub struct CachedUser {
@@ -24,9 +18,9 @@ pub struct CachedUser {
 }
 
 pub struct DefaultUserManager {
-    pub(crate) user_repo: Arc<UserRepository>,
-    identity_repo: Arc<PlatformIdentityRepository>,
-    analysis_repo: PostgresUserAnalysisRepository,
+    pub(crate) user_repo: Arc<dyn UserRepo + Send + Sync>,
+    identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+    analysis_repo: Arc<dyn UserAnalysisRepository>,
     pub user_cache: DashMap<(Platform, String), CachedUser>,
 }
 
@@ -34,9 +28,9 @@ const CACHE_MAX_AGE_SECS: i64 = 24 * 3600;
 
 impl DefaultUserManager {
     pub fn new(
-        user_repo: Arc<UserRepository>,
-        identity_repo: Arc<PlatformIdentityRepository>,
-        analysis_repo: PostgresUserAnalysisRepository,
+        user_repo: Arc<dyn UserRepo + Send + Sync>,
+        identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+        analysis_repo: Arc<dyn UserAnalysisRepository>,
     ) -> Self {
         Self {
             user_repo,
@@ -65,6 +59,11 @@ impl DefaultUserManager {
         self.user_cache.remove(&(platform, platform_user_id.to_string()));
     }
 
+    /// Drops every cached identity of `user_id`, e.g. after it was merged away.
+    pub fn invalidate_user_id(&self, user_id: Uuid) {
+        self.user_cache.retain(|_, cached| cached.user.user_id != user_id);
+    }
+
     async fn prune_cache(&self) {
         let now = Utc::now();
         let mut to_remove = Vec::new();
@@ -182,7 +181,7 @@ impl UserManager for DefaultUserManager {
         // ------------------------------------------------------------
         let now = Utc::now();
         let new_user_id = Uuid::new_v4();
-        let mut user = User {
+        let user = User {
             user_id: new_user_id,
             global_username: if lower_name.is_empty() { None } else { Some(lower_name.clone()) },
             created_at: now,
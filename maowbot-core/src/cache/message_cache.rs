@@ -1,328 +1,637 @@
+use arc_swap::ArcSwap;
 use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
-use tokio::sync::RwLock;
-use std::collections::VecDeque;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+use maowbot_common::models::analytics::ChatSearchQuery;
+use maowbot_common::traits::repository_traits::{ChatArchiveRepository, UserRepo};
 use crate::repositories::postgres::user_analysis::UserAnalysisRepository;
-pub(crate) use maowbot_common::models::cache::{CacheConfig, CachedMessage};
+use crate::Error;
+pub(crate) use maowbot_common::models::cache::{channel_key, CacheConfig, CachedMessage, ChannelRetention};
 
-/// Fixed-size ring buffer for chronological messages.
-struct GlobalRingBuffer {
+/// Ring size for channels when neither the channel nor the policy sets one.
+const DEFAULT_CHANNEL_CAPACITY: usize = 2_000;
+
+/// One channel's messages, oldest first. Each entry carries a cache-wide
+/// sequence number so reads across channels can be merged in arrival order.
+struct ChannelRing {
     capacity: usize,
-    messages: Vec<Option<CachedMessage>>,
-    start_idx: usize,
-    end_idx: usize,
-    total_count: usize,
+    max_age_seconds: Option<i64>,
+    messages: VecDeque<(u64, Arc<CachedMessage>)>,
 }
 
-impl GlobalRingBuffer {
-    fn new(capacity: usize) -> Self {
+impl ChannelRing {
+    fn new(capacity: usize, max_age_seconds: Option<i64>) -> Self {
         Self {
             capacity,
-            messages: vec![None; capacity],
-            start_idx: 0,
-            end_idx: 0,
-            total_count: 0,
+            max_age_seconds,
+            messages: VecDeque::with_capacity(capacity.min(DEFAULT_CHANNEL_CAPACITY)),
         }
     }
 
-    /// Push a new message, overwriting the oldest if full.
-    /// Returns the ring index where it was stored.
-    fn push(&mut self, msg: CachedMessage) -> usize {
+    /// Push a message, evicting the oldest ones past capacity or max age.
+    /// Returns (stored, evicted).
+    fn push(&mut self, seq: u64, msg: CachedMessage) -> (usize, usize) {
         if self.capacity == 0 {
-            return 0;
+            return (0, 0);
         }
-        if self.total_count == self.capacity {
-            // Overwrite oldest
-            self.start_idx = (self.start_idx + 1) % self.capacity;
-        } else {
-            self.total_count += 1;
+        let mut evicted = 0;
+        while self.messages.len() >= self.capacity {
+            self.messages.pop_front();
+            evicted += 1;
         }
-        let idx = self.end_idx;
-        self.messages[idx] = Some(msg);
-        self.end_idx = (self.end_idx + 1) % self.capacity;
-        idx
+        self.messages.push_back((seq, Arc::new(msg)));
+        evicted += self.trim_expired(Utc::now());
+        (1, evicted)
     }
 
-    /// Get the message by ring index, if it hasn't been overwritten.
-    fn get(&self, ring_idx: usize) -> Option<&CachedMessage> {
-        if self.capacity == 0 || self.total_count == 0 {
-            return None;
-        }
-        if !self.is_in_range(ring_idx) {
-            return None;
+    /// Removes messages older than this channel's max age from the front.
+    fn trim_expired(&mut self, now: DateTime<Utc>) -> usize {
+        let Some(max_age) = self.max_age_seconds else { return 0 };
+        let cutoff = now - Duration::seconds(max_age);
+        let mut removed = 0;
+        while self.messages.front().is_some_and(|(_, m)| m.timestamp < cutoff) {
+            self.messages.pop_front();
+            removed += 1;
         }
-        self.messages[ring_idx].as_ref()
+        removed
     }
 
-    /// Check if `ring_idx` is in the [start_idx .. start_idx + total_count) window (mod capacity).
-    fn is_in_range(&self, ring_idx: usize) -> bool {
-        if self.total_count == 0 || self.capacity == 0 {
-            return false;
+    /// Applies a new retention, dropping whatever no longer fits.
+    fn set_retention(&mut self, capacity: usize, max_age_seconds: Option<i64>) -> usize {
+        self.capacity = capacity;
+        self.max_age_seconds = max_age_seconds;
+        let mut removed = 0;
+        while self.messages.len() > self.capacity {
+            self.messages.pop_front();
+            removed += 1;
         }
-        let end_pos = (self.start_idx + self.total_count) % self.capacity;
-        if self.start_idx < end_pos {
-            ring_idx >= self.start_idx && ring_idx < end_pos
-        } else {
-            // wrap
-            ring_idx >= self.start_idx || ring_idx < end_pos
+        removed + self.trim_expired(Utc::now())
+    }
+}
+
+/// A channel's messages as of write `generation`, oldest first.
+struct Snapshot {
+    generation: u64,
+    messages: Vec<(u64, Arc<CachedMessage>)>,
+}
+
+/// One channel: writers change `ring` under its mutex and bump `generation`.
+/// Readers look at `snapshot`, a copy of the ring (message pointers only) that
+/// the first read after a write rebuilds, so a burst of writes costs one copy.
+struct ChannelShard {
+    ring: Mutex<ChannelRing>,
+    /// Bumped under `ring`'s lock after every change
+    generation: AtomicU64,
+    snapshot: ArcSwap<Snapshot>,
+}
+
+impl ChannelShard {
+    fn new(capacity: usize, max_age_seconds: Option<i64>) -> Self {
+        Self {
+            ring: Mutex::new(ChannelRing::new(capacity, max_age_seconds)),
+            generation: AtomicU64::new(0),
+            snapshot: ArcSwap::from_pointee(Snapshot { generation: 0, messages: Vec::new() }),
         }
     }
 
-    /// Removes messages older than `cutoff` from the front if possible.
-    /// Returns the count of removed messages.
-    fn trim_by_time(&mut self, cutoff: DateTime<Utc>) -> usize {
-        let mut removed = 0;
-        while self.total_count > 0 {
-            let oldest_idx = self.start_idx;
-            if let Some(msg) = &self.messages[oldest_idx] {
-                if msg.timestamp < cutoff {
-                    self.messages[oldest_idx] = None;
-                    self.start_idx = (oldest_idx + 1) % self.capacity;
-                    self.total_count -= 1;
-                    removed += 1;
-                } else {
-                    break;
-                }
-            } else {
-                // shouldn't happen if total_count is correct
-                self.start_idx = (oldest_idx + 1) % self.capacity;
-                self.total_count -= 1;
-                removed += 1;
-            }
+    /// Runs `f` on the ring and marks the published snapshot stale.
+    fn update<T>(&self, f: impl FnOnce(&mut ChannelRing) -> T) -> T {
+        let mut ring = self.ring.lock();
+        let out = f(&mut ring);
+        self.generation.fetch_add(1, Ordering::Release);
+        out
+    }
+
+    /// The current snapshot. Lock-free unless the ring changed since the last
+    /// read, in which case this reader rebuilds it under the ring's lock.
+    fn read(&self) -> Arc<Snapshot> {
+        let current = self.snapshot.load_full();
+        if current.generation == self.generation.load(Ordering::Acquire) {
+            return current;
         }
-        removed
+        let ring = self.ring.lock();
+        let generation = self.generation.load(Ordering::Acquire);
+        // Another reader may have rebuilt it while we waited for the lock
+        let current = self.snapshot.load_full();
+        if current.generation == generation {
+            return current;
+        }
+        let fresh = Arc::new(Snapshot { generation, messages: ring.messages.iter().cloned().collect() });
+        self.snapshot.store(fresh.clone());
+        fresh
     }
 }
 
-/// In-memory ChatCache with concurrency-friendly data structures.
+type Shard = Arc<ChannelShard>;
+
+/// In-memory ChatCache, sharded into one ring buffer per channel.
 ///
-/// - A `tokio::sync::RwLock<GlobalRingBuffer>` for the global ring of messages (fixed capacity).
-/// - A `DashMap<user_name, VecDeque<usize>>` storing ring indices for each user.
-/// - The `UserAnalysisRepository` for spam checks if you want to do that asynchronously.
+/// - Writers only lock their own channel's ring, so busy channels do not block each other.
+/// - The channel map and each ring are published as `ArcSwap` snapshots. Writes only
+///   mark a ring's snapshot stale; the first read after them copies the ring's message
+///   pointers under its lock, and every later read until the next write takes no locks.
+/// - Spam/quality trimming needs DB lookups, so it runs in batches from `spawn_trim_task`
+///   instead of on every message.
 pub struct ChatCache<R: UserAnalysisRepository> {
-    global: RwLock<GlobalRingBuffer>,
-    /// The key is now the `user_name`, so we can limit messages per "username".
-    user_map: DashMap<String, VecDeque<usize>>,
+    /// Channel key -> shard. Replaced as a whole when a channel first shows up.
+    shards: ArcSwap<HashMap<String, Shard>>,
+    /// Runtime per-channel overrides, seeded from `CacheConfig::channel_retention`
+    retention: DashMap<String, ChannelRetention>,
+    next_seq: AtomicU64,
     total_in_buffer: AtomicUsize,
     user_analysis_repo: R,
     config: CacheConfig,
 }
 
 impl<R: UserAnalysisRepository> ChatCache<R> {
-    /// Construct a new ChatCache, using `max_total_messages` as the ring's capacity.
     pub fn new(user_analysis_repo: R, config: CacheConfig) -> Self {
-        let capacity = config
-            .trim_policy
-            .max_total_messages
-            .unwrap_or(10_000);
-
-        let ring = GlobalRingBuffer::new(capacity);
+        let retention = config.channel_retention
+            .iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
         Self {
-            global: RwLock::new(ring),
-            user_map: DashMap::new(),
+            shards: ArcSwap::from_pointee(HashMap::new()),
+            retention,
+            next_seq: AtomicU64::new(0),
             total_in_buffer: AtomicUsize::new(0),
             user_analysis_repo,
             config,
         }
     }
 
-    /// Add a message to the cache, optionally doing a quick synchronous age trim.
-    /// (We do not do spam/quality checks here—do that in a separate background job.)
-    pub async fn add_message(&self, msg: CachedMessage) {
-        // -- 1) RING LOCK (write):
-        let idx = {
-            let mut guard = self.global.write().await;
-            let idx = guard.push(msg.clone());
-            self.total_in_buffer.store(guard.total_count, Ordering::Release);
-            idx
-        }; // ring write lock is dropped here
-
-        // -- 2) DASHMAP lock:
-        {
-            let mut user_q = self.user_map.entry(msg.user_name.clone()).or_default();
-            user_q.push_back(idx);
-            if let Some(max_per_user) = self.config.trim_policy.max_messages_per_user {
-                while user_q.len() > max_per_user {
-                    user_q.pop_front();
-                }
-            }
-        }
+    /// Number of messages currently cached across all channels.
+    pub fn len(&self) -> usize {
+        self.total_in_buffer.load(Ordering::Acquire)
+    }
 
-        // -- 3) (Optional) RING LOCK (write) to do age trimming:
-        if let Some(max_age) = self.config.trim_policy.max_age_seconds {
-            let cutoff = Utc::now() - Duration::seconds(max_age);
-            let removed = {
-                let mut guard = self.global.write().await;
-                let removed = guard.trim_by_time(cutoff);
-                self.total_in_buffer.store(guard.total_count, Ordering::Release);
-                removed
-            }; // ring write lock dropped
-
-            if removed > 0 {
-                self.remove_stale_indices();
-            }
-        }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    /// A separate function that you might call from a background task to remove messages from
-    /// users who exceed spam score or fail min_quality, etc.
-    pub async fn trim_spammy_users(&self) {
+    /// Effective (capacity, max age) for a channel key.
+    fn resolve_retention(&self, key: &str) -> (usize, Option<i64>) {
         let policy = &self.config.trim_policy;
-        if policy.spam_score_cutoff.is_none() && policy.min_quality_score.is_none() {
-            return;
+        let over = self.retention.get(key).map(|r| *r).unwrap_or_default();
+        let capacity = over.max_messages
+            .or(policy.max_messages_per_channel)
+            .unwrap_or(DEFAULT_CHANNEL_CAPACITY);
+        (capacity, over.max_age_seconds.or(policy.max_age_seconds))
+    }
+
+    fn shard(&self, key: &str) -> Option<Shard> {
+        self.shards.load().get(key).cloned()
+    }
+
+    fn shard_for(&self, key: &str) -> Shard {
+        if let Some(shard) = self.shard(key) {
+            return shard;
         }
-        let spam_cut = policy.spam_score_cutoff.unwrap_or(f32::MAX);
-        let quality_min = policy.min_quality_score.unwrap_or(-9999.0);
-
-        // 1) Gather user_names to purge
-        let mut purge_list = Vec::new();
-        for user_name in self.user_map.iter().map(|e| e.key().clone()) {
-            // Example approach: you could look up user_id in DB, then do scoring checks.
-            // Here, we just do dummy checks:
-            let dummy_spam_score = 0.0;
-            let dummy_quality_score = 1.0;
-            if dummy_spam_score >= spam_cut || dummy_quality_score < quality_min {
-                purge_list.push(user_name);
-            }
+        let (capacity, max_age) = self.resolve_retention(key);
+        let shard = Arc::new(ChannelShard::new(capacity, max_age));
+        // Another writer may add the channel first; `rcu` retries on top of theirs
+        self.shards.rcu(|shards| {
+            let mut shards = HashMap::clone(shards);
+            shards.entry(key.to_string()).or_insert_with(|| shard.clone());
+            shards
+        });
+        self.shard(key).unwrap_or(shard)
+    }
+
+    /// The current shard handles.
+    fn all_shards(&self) -> Vec<Shard> {
+        self.shards.load().values().cloned().collect()
+    }
+
+    fn adjust_total(&self, added: usize, removed: usize) {
+        if added > 0 {
+            self.total_in_buffer.fetch_add(added, Ordering::AcqRel);
         }
-        if purge_list.is_empty() {
-            return;
+        if removed > 0 {
+            self.total_in_buffer.fetch_sub(removed, Ordering::AcqRel);
         }
+    }
 
-        // 2) Remove those users' messages from user_map
-        for uname in purge_list {
-            if let Some(mut q) = self.user_map.get_mut(&uname) {
-                q.clear();
-            }
+    /// Add a message to its channel's ring. Only that channel is locked, and
+    /// only for the push; spam/quality checks happen later in `trim`.
+    pub fn add_message(&self, msg: CachedMessage) {
+        let shard = self.shard_for(&channel_key(&msg.platform, &msg.channel));
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let (added, evicted) = shard.update(|ring| ring.push(seq, msg));
+        self.adjust_total(added, evicted);
+    }
+
+    /// Adds already-seen messages (e.g. from the chat archive) oldest first, so
+    /// they sort before anything added later. Meant to run before live chat
+    /// arrives; returns how many are still cached after channel limits apply.
+    pub fn prefill(&self, mut messages: Vec<CachedMessage>) -> usize {
+        messages.sort_by_key(|m| m.timestamp);
+        let before = self.len();
+        for msg in messages {
+            self.add_message(msg);
         }
+        self.len().saturating_sub(before)
+    }
 
-        // 3) Rebuild ring for those that remain
-        {
-            let mut guard = self.global.write().await;
-            let capacity = guard.capacity;
-            let start_idx = guard.start_idx;
-            let total_count = guard.total_count;
-
-            let mut keep = Vec::with_capacity(total_count);
-            for i in 0..total_count {
-                let pos = (start_idx + i) % capacity;
-                if let Some(msg) = guard.messages[pos].take() {
-                    // If the user_map for this name is empty, skip it
-                    if let Some(q) = self.user_map.get(&msg.user_name) {
-                        if q.is_empty() {
-                            // skip
-                            continue;
-                        }
-                    }
-                    keep.push(Some(msg));
-                }
-            }
-            guard.messages.fill(None);
-            guard.start_idx = 0;
-            guard.end_idx = 0;
-            guard.total_count = 0;
-            for slot in keep {
-                if let Some(m) = slot {
-                    guard.push(m);
-                }
+    /// Prefills the cache with up to `max_messages` archived messages from the
+    /// last `hours` hours, so features that read recent chat work right after a
+    /// restart. Usernames are looked up once per user; roles aren't archived
+    /// and stay empty.
+    pub async fn warm_start(
+        &self,
+        archive: &dyn ChatArchiveRepository,
+        users: &(dyn UserRepo + Send + Sync),
+        hours: i64,
+        max_messages: i64,
+    ) -> Result<usize, Error> {
+        if hours <= 0 || max_messages <= 0 {
+            return Ok(0);
+        }
+        let query = ChatSearchQuery {
+            since: Some(Utc::now() - Duration::hours(hours)),
+            limit: max_messages,
+            ..Default::default()
+        };
+        let (archived, _) = archive.search_messages(&query).await?;
+
+        let mut names: HashMap<Uuid, String> = HashMap::new();
+        let mut messages = Vec::with_capacity(archived.len());
+        for m in archived {
+            if let std::collections::hash_map::Entry::Vacant(e) = names.entry(m.user_id) {
+                let name = match users.get(m.user_id).await {
+                    Ok(Some(u)) => u.global_username.unwrap_or_else(|| m.user_id.to_string()),
+                    _ => m.user_id.to_string(),
+                };
+                e.insert(name);
             }
-            self.total_in_buffer.store(guard.total_count, Ordering::Release);
+            messages.push(CachedMessage {
+                user_name: names[&m.user_id].clone(),
+                token_count: m.message_text.split_whitespace().count(),
+                platform: m.platform,
+                channel: m.channel,
+                user_id: m.user_id,
+                text: m.message_text,
+                timestamp: m.timestamp,
+                user_roles: Vec::new(),
+            });
         }
+        Ok(self.prefill(messages))
+    }
 
-        self.remove_stale_indices();
+    /// Overrides how much history one channel keeps. Takes effect immediately.
+    pub fn set_channel_retention(&self, platform: &str, channel: &str, retention: ChannelRetention) {
+        let key = channel_key(platform, channel);
+        self.retention.insert(key.clone(), retention);
+        let (capacity, max_age) = self.resolve_retention(&key);
+        if let Some(shard) = self.shard(&key) {
+            let removed = shard.update(|ring| ring.set_retention(capacity, max_age));
+            self.adjust_total(0, removed);
+        }
     }
 
-    /// Remove from each user's queue those ring indices that have been overwritten or trimmed.
-    fn remove_stale_indices(&self) {
-        let (start_idx, total_count, cap) = {
-            if let Ok(g) = self.global.try_read() {
-                (g.start_idx, g.total_count, g.capacity)
-            } else {
-                // if try_read fails, just skip
-                return;
-            }
-        };
+    /// Back to the policy defaults for this channel.
+    pub fn clear_channel_retention(&self, platform: &str, channel: &str) {
+        let key = channel_key(platform, channel);
+        self.retention.remove(&key);
+        let (capacity, max_age) = self.resolve_retention(&key);
+        if let Some(shard) = self.shard(&key) {
+            let removed = shard.update(|ring| ring.set_retention(capacity, max_age));
+            self.adjust_total(0, removed);
+        }
+    }
 
-        if total_count == 0 {
-            for mut entry in self.user_map.iter_mut() {
-                entry.clear();
-            }
-            self.total_in_buffer.store(0, Ordering::Release);
-            return;
+    /// Whether the channel has had chat since the cache started.
+    pub fn has_channel(&self, platform: &str, channel: &str) -> bool {
+        self.shard(&channel_key(platform, channel)).is_some()
+    }
+
+    /// Forgets a channel and its messages. Returns how many were cached.
+    pub fn remove_channel(&self, platform: &str, channel: &str) -> usize {
+        let key = channel_key(platform, channel);
+        let mut shard = None;
+        self.shards.rcu(|shards| {
+            let mut shards = HashMap::clone(shards);
+            shard = shards.remove(&key);
+            shards
+        });
+        let Some(shard) = shard else { return 0 };
+        let removed = shard.update(|ring| ring.messages.drain(..).count());
+        self.adjust_total(0, removed);
+        removed
+    }
+
+    /// Runs every batched trim: expired messages in quiet channels, spammy or
+    /// low-quality users, then the cache-wide cap. Returns the number removed.
+    pub async fn trim(&self) -> usize {
+        let mut removed = self.trim_expired();
+        removed += self.trim_spammy_users().await;
+        removed += self.enforce_total_cap();
+        removed
+    }
+
+    /// Age limits are applied on push, which never happens for channels that went quiet.
+    fn trim_expired(&self) -> usize {
+        let now = Utc::now();
+        let mut removed = 0;
+        for shard in self.all_shards() {
+            removed += shard.update(|ring| ring.trim_expired(now));
+        }
+        self.adjust_total(0, removed);
+        removed
+    }
+
+    /// Removes all messages from users whose spam score is at or above the cutoff,
+    /// or whose quality score is below the minimum. Scores are looked up once per
+    /// cached user with no ring locked.
+    pub async fn trim_spammy_users(&self) -> usize {
+        let policy = &self.config.trim_policy;
+        if policy.spam_score_cutoff.is_none() && policy.min_quality_score.is_none() {
+            return 0;
+        }
+        let spam_cut = policy.spam_score_cutoff.unwrap_or(f32::MAX);
+        let quality_min = policy.min_quality_score.unwrap_or(f32::MIN);
+
+        // 1) Distinct users currently cached
+        let mut users = HashSet::new();
+        for shard in self.all_shards() {
+            users.extend(shard.read().messages.iter().map(|(_, m)| m.user_id));
         }
 
-        let end_pos = (start_idx + total_count) % cap;
-        let in_range = move |ring_idx: usize| {
-            if start_idx < end_pos {
-                ring_idx >= start_idx && ring_idx < end_pos
-            } else {
-                ring_idx >= start_idx || ring_idx < end_pos
+        // 2) Score lookups
+        let mut purge: HashSet<Uuid> = HashSet::new();
+        for user_id in users {
+            match self.user_analysis_repo.get_analysis(user_id).await {
+                Ok(Some(a)) if a.spam_score >= spam_cut || a.quality_score < quality_min => {
+                    purge.insert(user_id);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("ChatCache: could not load analysis for {}: {:?}", user_id, e),
             }
-        };
+        }
+        if purge.is_empty() {
+            return 0;
+        }
 
-        for mut entry in self.user_map.iter_mut() {
-            entry.retain(|&idx| in_range(idx));
+        // 3) One write pass per channel
+        let mut removed = 0;
+        for shard in self.all_shards() {
+            removed += shard.update(|ring| {
+                let before = ring.messages.len();
+                ring.messages.retain(|(_, m)| !purge.contains(&m.user_id));
+                before - ring.messages.len()
+            });
         }
+        self.adjust_total(0, removed);
+        debug!("ChatCache: trimmed {} messages from {} users", removed, purge.len());
+        removed
+    }
 
-        if let Ok(g2) = self.global.try_read() {
-            self.total_in_buffer.store(g2.total_count, Ordering::Release);
+    /// Evicts the oldest messages across all channels until under `max_total_messages`.
+    fn enforce_total_cap(&self) -> usize {
+        let Some(max_total) = self.config.trim_policy.max_total_messages else { return 0 };
+        let mut removed = 0;
+        let shards = self.all_shards();
+        while self.len() > max_total {
+            // The shard whose oldest message arrived first
+            let oldest = shards.iter()
+                .filter_map(|s| s.read().messages.first().map(|(seq, _)| (*seq, s.clone())))
+                .min_by_key(|(seq, _)| *seq);
+            let Some((_, shard)) = oldest else { break };
+            if shard.update(|ring| ring.messages.pop_front()).is_some() {
+                self.adjust_total(0, 1);
+                removed += 1;
+            }
         }
+        removed
     }
 
-    /// Provide a get_recent_messages(...) method for retrieving messages with optional token_limit
-    pub async fn get_recent_messages(
+    /// Messages since `since` across all channels (optionally only one user's),
+    /// oldest first, stopping once `token_limit` would be exceeded.
+    pub fn get_recent_messages(
         &self,
         since: DateTime<Utc>,
         token_limit: Option<usize>,
         filter_user_name: Option<&str>,
     ) -> Vec<CachedMessage> {
-        let mut results = Vec::new();
-        let global_guard = self.global.read().await;
-
-        let capacity = global_guard.capacity;
-        let start_idx = global_guard.start_idx;
-        let total_count = global_guard.total_count;
-        if total_count == 0 {
-            return results;
+        let mut matches: Vec<(u64, Arc<CachedMessage>)> = Vec::new();
+        for shard in self.all_shards() {
+            matches.extend(
+                shard.read().messages.iter()
+                    .filter(|(_, m)| m.timestamp >= since)
+                    .filter(|(_, m)| filter_user_name.is_none_or(|u| m.user_name == u))
+                    .cloned()
+            );
         }
+        matches.sort_unstable_by_key(|(seq, _)| *seq);
 
-        let mut tokens_used = 0usize;
-
-        if let Some(uname) = filter_user_name {
-            if let Some(q) = self.user_map.get(uname) {
-                for &idx in q.iter() {
-                    if let Some(m) = global_guard.get(idx) {
-                        if m.timestamp < since {
-                            continue;
-                        }
-                        if let Some(tlimit) = token_limit {
-                            if tokens_used + m.token_count > tlimit {
-                                break;
-                            }
-                            tokens_used += m.token_count;
-                        }
-                        results.push(m.clone());
-                    }
-                }
+        if filter_user_name.is_some() {
+            if let Some(max_per_user) = self.config.trim_policy.max_messages_per_user {
+                let skip = matches.len().saturating_sub(max_per_user);
+                matches.drain(..skip);
             }
-        } else {
-            // Global scan from oldest to newest
-            for i in 0..total_count {
-                let ring_pos = (start_idx + i) % capacity;
-                if let Some(msg) = global_guard.messages[ring_pos].as_ref() {
-                    if msg.timestamp >= since {
-                        if let Some(tlimit) = token_limit {
-                            if tokens_used + msg.token_count > tlimit {
-                                break;
-                            }
-                            tokens_used += msg.token_count;
-                        }
-                        results.push(msg.clone());
-                    }
+        }
+        take_within_tokens(matches.iter().map(|(_, m)| m.as_ref()), token_limit)
+    }
+
+    /// Messages since `since` in one channel, oldest first. Only that channel is read.
+    pub fn get_channel_messages(
+        &self,
+        platform: &str,
+        channel: &str,
+        since: DateTime<Utc>,
+        token_limit: Option<usize>,
+    ) -> Vec<CachedMessage> {
+        let Some(shard) = self.shard(&channel_key(platform, channel)) else {
+            return Vec::new();
+        };
+        take_within_tokens(
+            shard.read().messages.iter().filter(|(_, m)| m.timestamp >= since).map(|(_, m)| m.as_ref()),
+            token_limit,
+        )
+    }
+}
+
+impl<R: UserAnalysisRepository + 'static> ChatCache<R> {
+    /// Runs `trim` every `every` until the cache is dropped everywhere else.
+    pub fn spawn_trim_task(cache: Arc<Self>, every: std::time::Duration) -> JoinHandle<()> {
+        let weak = Arc::downgrade(&cache);
+        drop(cache);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            interval.tick().await; // first tick fires immediately
+            loop {
+                interval.tick().await;
+                let Some(cache) = weak.upgrade() else { break };
+                let removed = cache.trim().await;
+                if removed > 0 {
+                    debug!("ChatCache: background trim removed {} messages ({} cached)", removed, cache.len());
                 }
             }
+        })
+    }
+}
+
+/// Takes messages in order until the next one would exceed `token_limit`.
+fn take_within_tokens<'a>(
+    messages: impl Iterator<Item = &'a CachedMessage>,
+    token_limit: Option<usize>,
+) -> Vec<CachedMessage> {
+    let mut tokens_used = 0usize;
+    let mut out = Vec::new();
+    for msg in messages {
+        if let Some(limit) = token_limit {
+            if tokens_used + msg.token_count > limit {
+                break;
+            }
+            tokens_used += msg.token_count;
+        }
+        out.push(msg.clone());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use maowbot_common::models::cache::TrimPolicy;
+    use maowbot_common::models::user_analysis::UserAnalysis;
+    use std::collections::HashMap;
+    use crate::Error;
+
+    #[derive(Default)]
+    struct MockAnalysisRepo {
+        data: HashMap<Uuid, UserAnalysis>,
+    }
+
+    #[async_trait]
+    impl UserAnalysisRepository for MockAnalysisRepo {
+        async fn create_analysis(&self, _: &UserAnalysis) -> Result<(), Error> { Ok(()) }
+        async fn get_analysis(&self, user_id: Uuid) -> Result<Option<UserAnalysis>, Error> {
+            Ok(self.data.get(&user_id).cloned())
+        }
+        async fn update_analysis(&self, _: &UserAnalysis) -> Result<(), Error> { Ok(()) }
+        async fn insert_history(&self, _: &str, _: &UserAnalysis) -> Result<(), Error> { Ok(()) }
+    }
+
+    fn policy() -> TrimPolicy {
+        TrimPolicy {
+            max_age_seconds: None,
+            spam_score_cutoff: None,
+            max_total_messages: None,
+            max_messages_per_channel: Some(3),
+            max_messages_per_user: None,
+            min_quality_score: None,
         }
-        results
     }
-}
\ No newline at end of file
+
+    fn msg(channel: &str, user_id: Uuid, text: &str) -> CachedMessage {
+        CachedMessage {
+            platform: "twitch-irc".into(),
+            channel: channel.into(),
+            user_id,
+            user_name: user_id.to_string(),
+            text: text.into(),
+            timestamp: Utc::now(),
+            token_count: 1,
+            user_roles: vec![],
+        }
+    }
+
+    #[test]
+    fn test_channels_have_separate_rings() {
+        let mut config = CacheConfig::new(policy());
+        config.channel_retention.insert(
+            channel_key("twitch-irc", "#quiet"),
+            ChannelRetention { max_messages: Some(1), max_age_seconds: None },
+        );
+        let cache = ChatCache::new(MockAnalysisRepo::default(), config);
+        let user = Uuid::new_v4();
+        for i in 0..5 {
+            cache.add_message(msg("#busy", user, &format!("busy {}", i)));
+            cache.add_message(msg("#Quiet", user, &format!("quiet {}", i)));
+        }
+
+        let since = Utc::now() - Duration::minutes(1);
+        let busy: Vec<_> = cache.get_channel_messages("twitch-irc", "busy", since, None)
+            .into_iter().map(|m| m.text).collect();
+        assert_eq!(busy, ["busy 2", "busy 3", "busy 4"]);
+        let quiet = cache.get_channel_messages("twitch-irc", "#quiet", since, None);
+        assert_eq!(quiet.len(), 1);
+        assert_eq!(quiet[0].text, "quiet 4");
+
+        // Merged across channels in arrival order
+        let all: Vec<_> = cache.get_recent_messages(since, None, None)
+            .into_iter().map(|m| m.text).collect();
+        assert_eq!(all, ["busy 2", "busy 3", "busy 4", "quiet 4"]);
+        assert_eq!(cache.len(), 4);
+
+        cache.set_channel_retention("twitch-irc", "#busy", ChannelRetention { max_messages: Some(2), max_age_seconds: None });
+        assert_eq!(cache.get_channel_messages("twitch-irc", "#busy", since, None).len(), 2);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_prefill_keeps_archive_order_before_live_messages() {
+        let cache = ChatCache::new(MockAnalysisRepo::default(), CacheConfig::new(policy()));
+        let user = Uuid::new_v4();
+        let now = Utc::now();
+        let mut older = msg("#a", user, "older");
+        older.timestamp = now - Duration::minutes(10);
+        let mut newer = msg("#a", user, "newer");
+        newer.timestamp = now - Duration::minutes(5);
+
+        assert_eq!(cache.prefill(vec![newer, older]), 2);
+        cache.add_message(msg("#a", user, "live"));
+        let texts: Vec<_> = cache.get_channel_messages("twitch-irc", "#a", now - Duration::hours(1), None)
+            .into_iter().map(|m| m.text).collect();
+        assert_eq!(texts, ["older", "newer", "live"]);
+    }
+
+    #[test]
+    fn test_reads_do_not_wait_on_writers() {
+        let cache = ChatCache::new(MockAnalysisRepo::default(), CacheConfig::new(policy()));
+        let user = Uuid::new_v4();
+        cache.add_message(msg("#a", user, "hi"));
+
+        let since = Utc::now() - Duration::minutes(1);
+        assert_eq!(cache.get_channel_messages("twitch-irc", "#a", since, None).len(), 1);
+
+        // Once a read has published the snapshot, a writer holding the ring doesn't block readers
+        let shard = cache.shard(&channel_key("twitch-irc", "#a")).unwrap();
+        let _writer = shard.ring.lock();
+        assert_eq!(cache.get_channel_messages("twitch-irc", "#a", since, None).len(), 1);
+        assert_eq!(cache.get_recent_messages(since, None, None).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_batched_trim() {
+        let spammer = Uuid::new_v4();
+        let regular = Uuid::new_v4();
+        let mut repo = MockAnalysisRepo::default();
+        let mut analysis = UserAnalysis::new(spammer);
+        analysis.spam_score = 0.9;
+        repo.data.insert(spammer, analysis);
+
+        let mut trim_policy = policy();
+        trim_policy.spam_score_cutoff = Some(0.8);
+        trim_policy.max_total_messages = Some(2);
+        let cache = ChatCache::new(repo, CacheConfig::new(trim_policy));
+        cache.add_message(msg("#a", spammer, "spam"));
+        cache.add_message(msg("#a", regular, "one"));
+        cache.add_message(msg("#b", regular, "two"));
+        cache.add_message(msg("#b", regular, "three"));
+        assert_eq!(cache.len(), 4);
+
+        // Spammer's message goes first, then the oldest one over the cap
+        assert_eq!(cache.trim().await, 2);
+        let texts: Vec<_> = cache.get_recent_messages(Utc::now() - Duration::minutes(1), None, None)
+            .into_iter().map(|m| m.text).collect();
+        assert_eq!(texts, ["two", "three"]);
+    }
+}
@@ -5,18 +5,34 @@ use aes_gcm::{
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use rand::rngs::OsRng;
 use rand_core::TryRngCore;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use crate::Error;
 
+pub mod rotation;
+pub mod secrets;
+
+/// AES-256-GCM encryption for secrets stored in the database.
+///
+/// Clones share the key, so `replace_key` on any clone (after a key rotation)
+/// takes effect for every repository holding one.
 #[derive(Clone)]
 pub struct Encryptor {
-    cipher: Arc<Aes256Gcm>,
+    cipher: Arc<RwLock<Arc<Aes256Gcm>>>,
+    /// Shared by secret writes in flight, exclusive during a key rotation
+    writes: Arc<tokio::sync::RwLock<()>>,
 }
 
 impl Encryptor {
     /// Creates a new `Encryptor` using a 32‐byte key for AES‐256.
     pub fn new(key_bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            cipher: Arc::new(RwLock::new(Arc::new(Self::build_cipher(key_bytes)?))),
+            writes: Arc::new(tokio::sync::RwLock::new(())),
+        })
+    }
+
+    fn build_cipher(key_bytes: &[u8]) -> Result<Aes256Gcm, Error> {
         // AES-256-GCM requires a 256-bit (32 bytes) key.
         if key_bytes.len() != 32 {
             return Err(Error::KeyDerivation(
@@ -27,11 +43,31 @@ impl Encryptor {
         let key = Key::<Aes256Gcm>::clone_from_slice(key_bytes);
 
         // Initialize the AES-GCM cipher.
-        let cipher = Aes256Gcm::new(&key);
+        Ok(Aes256Gcm::new(&key))
+    }
 
-        Ok(Self {
-            cipher: Arc::new(cipher),
-        })
+    /// Switch every clone of this `Encryptor` to a new key.
+    pub fn replace_key(&self, key_bytes: &[u8]) -> Result<(), Error> {
+        let cipher = Arc::new(Self::build_cipher(key_bytes)?);
+        *self.cipher.write().unwrap() = cipher;
+        Ok(())
+    }
+
+    /// Hold from encrypting a secret until it's written to the database, so a
+    /// key rotation can't re-encrypt the table in between and leave the value
+    /// under the old key.
+    pub async fn write_guard(&self) -> tokio::sync::RwLockReadGuard<'_, ()> {
+        self.writes.read().await
+    }
+
+    /// Waits for secret writes in flight and holds off new ones until dropped;
+    /// a key rotation keeps it from before re-encrypting until `replace_key`.
+    pub async fn rotation_guard(&self) -> tokio::sync::RwLockWriteGuard<'_, ()> {
+        self.writes.write().await
+    }
+
+    fn cipher(&self) -> Arc<Aes256Gcm> {
+        self.cipher.read().unwrap().clone()
     }
 
     /// Encrypts `data` into base64(`nonce || ciphertext`).
@@ -49,7 +85,7 @@ impl Encryptor {
         let nonce = Nonce::from_slice(&nonce_bytes);
 
         // Encrypt the data. On failure, map the error to our custom `Error`.
-        let ciphertext = self.cipher
+        let ciphertext = self.cipher()
             .encrypt(nonce, data.as_bytes())
             .map_err(|e| Error::Encryption(e.to_string()))?;
 
@@ -78,7 +114,7 @@ impl Encryptor {
         let nonce = Nonce::from_slice(nonce_bytes);
 
         // Decrypt with AES-GCM. On failure, map the error to our custom `Error`.
-        let plaintext = self.cipher
+        let plaintext = self.cipher()
             .decrypt(nonce, ciphertext)
             .map_err(|e| Error::Decryption(e.to_string()))?;
 
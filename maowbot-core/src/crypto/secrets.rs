@@ -0,0 +1,360 @@
+// Master key storage for the credential `Encryptor`.
+//
+// The 32-byte AES key is looked up in this order:
+//   1. `MAOWBOT_MASTER_KEY` (base64), for headless installs and CI
+//   2. the OS keyring (KWallet, GNOME Keyring, Windows Credential Manager, macOS Keychain)
+//   3. the fallback key file `<config dir>/maowbot/master.key` (mode 0600)
+//
+// The key file is sealed with AES-256-GCM under a key derived (Argon2id) from
+// `MAOWBOT_KEYFILE_PASSPHRASE` when that is set. Without a passphrase it holds
+// the key as plain base64, protected only by its 0600 permissions; a plain
+// file found while a passphrase is set is sealed in place.
+//
+// A new key is only generated when none of these hold one; if it cannot be
+// persisted, startup fails instead of running with a key that is lost on
+// restart (which would leave every stored credential unreadable).
+
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use keyring::Entry;
+use rand::rngs::OsRng;
+use rand_core::TryRngCore;
+use tracing::{info, warn};
+
+use crate::Error;
+
+const KEYRING_SERVICE: &str = "maowbot";
+const KEYRING_USER: &str = "master-key";
+const KEYRING_PREVIOUS_USER: &str = "master-key.previous";
+pub const MASTER_KEY_ENV: &str = "MAOWBOT_MASTER_KEY";
+pub const KEYFILE_PASSPHRASE_ENV: &str = "MAOWBOT_KEYFILE_PASSPHRASE";
+/// Marks a key file sealed with a passphrase: base64(salt || nonce || ciphertext) follows.
+const SEALED_PREFIX: &str = "argon2id-aes256gcm:";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Where the active master key came from (and where a rotated key is written back).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySource {
+    Environment,
+    Keyring,
+    KeyFile(PathBuf),
+}
+
+impl fmt::Display for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeySource::Environment => write!(f, "environment (${})", MASTER_KEY_ENV),
+            KeySource::Keyring => write!(f, "OS keyring"),
+            KeySource::KeyFile(path) => write!(f, "key file {}", path.display()),
+        }
+    }
+}
+
+pub struct SecretsManager {
+    key: [u8; 32],
+    source: KeySource,
+    /// Seals the key file, if one is used
+    passphrase: Option<String>,
+}
+
+impl SecretsManager {
+    /// Load the master key, generating and persisting one on first run.
+    pub fn load_or_create() -> Result<Self, Error> {
+        if let Ok(encoded) = std::env::var(MASTER_KEY_ENV) {
+            let key = decode_key(encoded.trim())
+                .map_err(|e| Error::KeyDerivation(format!("{} is invalid: {}", MASTER_KEY_ENV, e)))?;
+            info!("Using master key from ${}", MASTER_KEY_ENV);
+            return Ok(Self { key, source: KeySource::Environment, passphrase: None });
+        }
+
+        let key_file = default_key_file()?;
+        let passphrase = std::env::var(KEYFILE_PASSPHRASE_ENV).ok().filter(|p| !p.is_empty());
+        let keyring_usable = match Entry::new(KEYRING_SERVICE, KEYRING_USER) {
+            Ok(entry) => match entry.get_password() {
+                Ok(encoded) => {
+                    // Never replace an existing but unreadable key: that would orphan every secret
+                    let key = decode_key(&encoded).map_err(|e| Error::Keyring(format!(
+                        "Master key in the OS keyring is corrupt ({}); refusing to generate a new one", e
+                    )))?;
+                    info!("Retrieved existing master key from system keyring");
+                    return Ok(Self { key, source: KeySource::Keyring, passphrase });
+                }
+                Err(keyring::Error::NoEntry) => true,
+                Err(e) => {
+                    warn!("System keyring unavailable ({}); using key file fallback", e);
+                    false
+                }
+            },
+            Err(e) => {
+                warn!("Couldn't create keyring entry ({}); using key file fallback", e);
+                false
+            }
+        };
+
+        if let Some((key, sealed)) = read_key_file(&key_file, passphrase.as_deref())? {
+            info!("Retrieved master key from {}", key_file.display());
+            if !sealed {
+                if passphrase.is_some() {
+                    write_key_file(&key_file, &key, passphrase.as_deref())?;
+                    info!("Sealed {} with ${}", key_file.display(), KEYFILE_PASSPHRASE_ENV);
+                } else {
+                    warn!("{} is not encrypted; set ${} to seal it", key_file.display(), KEYFILE_PASSPHRASE_ENV);
+                }
+            }
+            return Ok(Self { key, source: KeySource::KeyFile(key_file), passphrase });
+        }
+
+        // First run: nothing stored anywhere
+        let key = generate_key()?;
+        if keyring_usable {
+            match store_in_keyring(KEYRING_USER, &key) {
+                Ok(()) => {
+                    info!("Stored new master key in system keyring");
+                    return Ok(Self { key, source: KeySource::Keyring, passphrase });
+                }
+                Err(e) => warn!("Failed to store key in system keyring ({}); using key file fallback", e),
+            }
+        }
+        write_key_file(&key_file, &key, passphrase.as_deref())?;
+        if passphrase.is_none() {
+            warn!("{} is not encrypted; set ${} to seal it", key_file.display(), KEYFILE_PASSPHRASE_ENV);
+        }
+        info!("Stored new master key in {}", key_file.display());
+        Ok(Self { key, source: KeySource::KeyFile(key_file), passphrase })
+    }
+
+    pub fn key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    pub fn source(&self) -> &KeySource {
+        &self.source
+    }
+
+    /// Whether `replace_key` can write a new key back to the current source.
+    pub fn can_rotate(&self) -> bool {
+        self.source != KeySource::Environment
+    }
+
+    /// Persist `new_key` as the master key, keeping the old one as a backup next to it.
+    ///
+    /// The backup (`master-key.previous` in the keyring, `master.key.previous` on
+    /// disk) lets credentials be recovered if a rotation is interrupted.
+    pub fn replace_key(&mut self, new_key: [u8; 32]) -> Result<(), Error> {
+        match &self.source {
+            KeySource::Environment => {
+                return Err(Error::KeyDerivation(format!(
+                    "The master key comes from ${}; set a new value there instead", MASTER_KEY_ENV
+                )));
+            }
+            KeySource::Keyring => {
+                store_in_keyring(KEYRING_PREVIOUS_USER, &self.key)?;
+                store_in_keyring(KEYRING_USER, &new_key)?;
+            }
+            KeySource::KeyFile(path) => {
+                write_key_file(&previous_path(path), &self.key, self.passphrase.as_deref())?;
+                write_key_file(path, &new_key, self.passphrase.as_deref())?;
+            }
+        }
+        self.key = new_key;
+        Ok(())
+    }
+}
+
+/// A fresh random 256-bit key.
+pub fn generate_key() -> Result<[u8; 32], Error> {
+    let mut key = [0u8; 32];
+    OsRng.try_fill_bytes(&mut key)
+        .map_err(|e| Error::KeyDerivation(e.to_string()))?;
+    Ok(key)
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; 32], Error> {
+    let bytes = BASE64.decode(encoded.trim())
+        .map_err(|e| Error::Parse(format!("Failed to decode key: {}", e)))?;
+    let len = bytes.len();
+    bytes.try_into()
+        .map_err(|_| Error::Parse(format!("Key was not 32 bytes (got {} bytes)", len)))
+}
+
+fn store_in_keyring(user: &str, key: &[u8; 32]) -> Result<(), Error> {
+    let entry = Entry::new(KEYRING_SERVICE, user)?;
+    entry.set_password(&BASE64.encode(key))?;
+    Ok(())
+}
+
+fn default_key_file() -> Result<PathBuf, Error> {
+    dirs::config_dir()
+        .map(|dir| dir.join("maowbot").join("master.key"))
+        .ok_or_else(|| Error::Keyring("Could not determine config directory".to_string()))
+}
+
+fn previous_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".previous");
+    PathBuf::from(name)
+}
+
+fn keyfile_cipher(passphrase: &str, salt: &[u8]) -> Result<Aes256Gcm, Error> {
+    let mut kek = [0u8; 32];
+    Argon2::default().hash_password_into(passphrase.as_bytes(), salt, &mut kek)
+        .map_err(|e| Error::KeyDerivation(format!("Could not derive the key file key: {}", e)))?;
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&kek)))
+}
+
+fn seal_key(key: &[u8; 32], passphrase: &str) -> Result<String, Error> {
+    let mut salt_and_nonce = [0u8; SALT_LEN + NONCE_LEN];
+    OsRng.try_fill_bytes(&mut salt_and_nonce)
+        .map_err(|e| Error::KeyDerivation(e.to_string()))?;
+    let (salt, nonce) = salt_and_nonce.split_at(SALT_LEN);
+    let ciphertext = keyfile_cipher(passphrase, salt)?
+        .encrypt(Nonce::from_slice(nonce), key.as_slice())
+        .map_err(|e| Error::Encryption(format!("Could not seal the key file: {}", e)))?;
+    let mut sealed = salt_and_nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(sealed)))
+}
+
+fn open_sealed_key(encoded: &str, passphrase: &str) -> Result<[u8; 32], Error> {
+    let sealed = BASE64.decode(encoded.trim())
+        .map_err(|e| Error::Parse(format!("Failed to decode key: {}", e)))?;
+    if sealed.len() <= SALT_LEN + NONCE_LEN {
+        return Err(Error::Parse("Sealed key is truncated".to_string()));
+    }
+    let (salt, rest) = sealed.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let key = keyfile_cipher(passphrase, salt)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| Error::Decryption("wrong passphrase".to_string()))?;
+    let len = key.len();
+    key.try_into()
+        .map_err(|_| Error::Parse(format!("Key was not 32 bytes (got {} bytes)", len)))
+}
+
+/// The key in `path` and whether the file was sealed with a passphrase.
+fn read_key_file(path: &Path, passphrase: Option<&str>) -> Result<Option<([u8; 32], bool)>, Error> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)?;
+    let result = match (contents.trim().strip_prefix(SEALED_PREFIX), passphrase) {
+        (Some(sealed), Some(passphrase)) => open_sealed_key(sealed, passphrase).map(|key| (key, true)),
+        (Some(_), None) => {
+            return Err(Error::Keyring(format!(
+                "Master key file {} is encrypted; set ${} to unlock it", path.display(), KEYFILE_PASSPHRASE_ENV
+            )));
+        }
+        (None, _) => decode_key(&contents).map(|key| (key, false)),
+    };
+    result.map(Some).map_err(|e| Error::Keyring(format!(
+        "Master key file {} could not be read ({}); refusing to generate a new one", path.display(), e
+    )))
+}
+
+fn write_key_file(path: &Path, key: &[u8; 32], passphrase: Option<&str>) -> Result<(), Error> {
+    let contents = match passphrase {
+        Some(passphrase) => seal_key(key, passphrase)?,
+        None => BASE64.encode(key),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Write to a temp file and rename so a crash never leaves a truncated key behind.
+    // A leftover temp file is removed first: its permissions can't be trusted.
+    let tmp = path.with_extension("tmp");
+    match std::fs::remove_file(&tmp) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        // Owner-only from the moment it exists, never readable by others
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_file_round_trip_and_rotation() {
+        let dir = std::env::temp_dir().join(format!("maowbot-secrets-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("master.key");
+        let first = generate_key().unwrap();
+        write_key_file(&path, &first, None).unwrap();
+        assert_eq!(read_key_file(&path, None).unwrap(), Some((first, false)));
+
+        let mut secrets = SecretsManager { key: first, source: KeySource::KeyFile(path.clone()), passphrase: None };
+        let second = generate_key().unwrap();
+        secrets.replace_key(second).unwrap();
+        assert_eq!(read_key_file(&path, None).unwrap(), Some((second, false)));
+        assert_eq!(read_key_file(&previous_path(&path), None).unwrap(), Some((first, false)));
+
+        std::fs::write(&path, "not a key").unwrap();
+        assert!(read_key_file(&path, None).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sealed_key_file_needs_the_passphrase() {
+        let dir = std::env::temp_dir().join(format!("maowbot-secrets-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("master.key");
+        let key = generate_key().unwrap();
+        write_key_file(&path, &key, Some("correct horse")).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with(SEALED_PREFIX));
+        assert!(!contents.contains(&BASE64.encode(key)));
+        assert_eq!(read_key_file(&path, Some("correct horse")).unwrap(), Some((key, true)));
+        assert!(read_key_file(&path, Some("battery staple")).is_err());
+        assert!(read_key_file(&path, None).is_err());
+
+        // Rotation keeps both the new key and the backup sealed
+        let mut secrets = SecretsManager {
+            key,
+            source: KeySource::KeyFile(path.clone()),
+            passphrase: Some("correct horse".to_string()),
+        };
+        let second = generate_key().unwrap();
+        secrets.replace_key(second).unwrap();
+        assert_eq!(read_key_file(&path, Some("correct horse")).unwrap(), Some((second, true)));
+        assert_eq!(read_key_file(&previous_path(&path), Some("correct horse")).unwrap(), Some((key, true)));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("maowbot-secrets-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("master.key");
+        // A leftover world-readable temp file must not be reused
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(path.with_extension("tmp"), "stale").unwrap();
+        std::fs::set_permissions(path.with_extension("tmp"), std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_key_file(&path, &generate_key().unwrap(), None).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,471 @@
+// maowbot-core/src/db/backup.rs
+//
+// Full backups: one JSON archive with everything needed to rebuild the bot on
+// a fresh install (workspaces and their channel routes, users, identities,
+// credentials, platform config, bot config, commands, redeems and pipelines).
+//
+// Credentials, platform client secrets and bot config secrets are sealed with a one-time backup key
+// rather than the server's master key, because a new machine has a different
+// master key. The key is shown once at export and is needed again to restore them.
+
+use std::collections::HashMap;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use maowbot_common::models::command::Command;
+use maowbot_common::models::event_pipeline::{
+    CreateActionRequest, CreateFilterRequest, CreatePipelineRequest, EventPipeline, PipelineAction, PipelineFilter,
+};
+use maowbot_common::models::platform::{PlatformConfig, PlatformCredential, PlatformIdentity};
+use maowbot_common::models::redeem::Redeem;
+use maowbot_common::models::user::User;
+use maowbot_common::models::workspace::{Workspace, WorkspaceChannel};
+use maowbot_common::traits::event_pipeline_traits::EventPipelineRepository;
+
+use crate::crypto::{secrets::generate_key, Encryptor};
+use crate::db::transfer::{BackendRepos, ALL_PLATFORMS};
+use crate::db::Database;
+use crate::Error;
+
+pub const BACKUP_FORMAT: &str = "maowbot-backup";
+/// Bumped when the archive layout itself changes.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_KEY_PREFIX: &str = "mwbk_";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupArchive {
+    pub format: String,
+    pub format_version: u32,
+    /// Newest database migration of the server that wrote the backup.
+    pub schema_version: i64,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub workspaces: Vec<Workspace>,
+    /// Chat channels routed to a workspace (absent from older archives).
+    #[serde(default)]
+    pub channel_routes: Vec<WorkspaceChannel>,
+    pub users: Vec<User>,
+    pub identities: Vec<PlatformIdentity>,
+    pub workspace_data: Vec<WorkspaceBackup>,
+    pub pipelines: Vec<PipelineBackup>,
+    /// `BackupSecrets` as JSON, encrypted with the backup key.
+    pub sealed_secrets: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceBackup {
+    pub workspace_id: Uuid,
+    pub bot_config: Vec<ConfigBackup>,
+    pub commands: Vec<Command>,
+    pub redeems: Vec<Redeem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBackup {
+    pub key: String,
+    pub value: String,
+    pub meta: Option<JsonValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineBackup {
+    pub pipeline: EventPipeline,
+    pub filters: Vec<PipelineFilter>,
+    pub actions: Vec<PipelineAction>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BackupSecrets {
+    platform_configs: Vec<PlatformConfig>,
+    /// Each credential with the workspace it belongs to.
+    credentials: Vec<(Uuid, PlatformCredential)>,
+    /// Bot config secrets as (workspace, key, value).
+    #[serde(default)]
+    config_secrets: Vec<(Uuid, String, String)>,
+}
+
+/// Number of items per kind, for export summaries and import results.
+#[derive(Debug, Default, Clone)]
+pub struct BackupCounts {
+    pub workspaces: usize,
+    pub users: usize,
+    pub identities: usize,
+    pub platform_configs: usize,
+    pub credentials: usize,
+    pub bot_config: usize,
+    pub commands: usize,
+    pub redeems: usize,
+    pub pipelines: usize,
+}
+
+/// Result of `import_backup`: what was written, what already existed, and what
+/// could not be restored.
+#[derive(Debug, Default, Clone)]
+pub struct ImportReport {
+    pub restored: BackupCounts,
+    pub skipped: BackupCounts,
+    pub warnings: Vec<String>,
+}
+
+impl BackupArchive {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Parses an archive and checks this server can restore it.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let archive: Self = serde_json::from_slice(data)
+            .map_err(|e| Error::Parse(format!("Not a MaowBot backup archive: {}", e)))?;
+        if archive.format != BACKUP_FORMAT {
+            return Err(Error::Parse(format!("Not a MaowBot backup archive (format '{}')", archive.format)));
+        }
+        if archive.format_version > BACKUP_FORMAT_VERSION {
+            return Err(Error::ValidationError(format!(
+                "Backup format v{} is newer than this server supports (v{}); upgrade MaowBot first",
+                archive.format_version, BACKUP_FORMAT_VERSION
+            )));
+        }
+        let schema_version = Database::schema_version();
+        if archive.schema_version > schema_version {
+            return Err(Error::ValidationError(format!(
+                "Backup was taken at schema version {} but this server is at {}; upgrade MaowBot first",
+                archive.schema_version, schema_version
+            )));
+        }
+        Ok(archive)
+    }
+
+    pub fn counts(&self) -> BackupCounts {
+        BackupCounts {
+            workspaces: self.workspaces.len(),
+            users: self.users.len(),
+            identities: self.identities.len(),
+            platform_configs: 0,
+            credentials: 0,
+            bot_config: self.workspace_data.iter().map(|w| w.bot_config.len()).sum(),
+            commands: self.workspace_data.iter().map(|w| w.commands.len()).sum(),
+            redeems: self.workspace_data.iter().map(|w| w.redeems.len()).sum(),
+            pipelines: self.pipelines.len(),
+        }
+    }
+}
+
+fn backup_encryptor(backup_key: &str) -> Result<Encryptor, Error> {
+    let encoded = backup_key.trim().strip_prefix(BACKUP_KEY_PREFIX)
+        .ok_or_else(|| Error::ValidationError(format!("Backup keys start with '{}'", BACKUP_KEY_PREFIX)))?;
+    let key = URL_SAFE_NO_PAD.decode(encoded)
+        .map_err(|_| Error::ValidationError("Backup key is not valid".into()))?;
+    Encryptor::new(&key)
+}
+
+/// Snapshot everything restorable into an archive. Returns the archive, the
+/// backup key sealing its secrets, and the number of items of each kind.
+pub async fn export_backup(
+    repos: &BackendRepos,
+    pipelines: &dyn EventPipelineRepository,
+) -> Result<(BackupArchive, String, BackupCounts), Error> {
+    let workspaces = repos.workspaces.list_workspaces().await?;
+    let channel_routes = repos.workspaces.list_channels().await?;
+    let users = repos.users.list_all().await?;
+    let mut identities = Vec::new();
+    for user in &users {
+        identities.extend(repos.identities.get_all_for_user(user.user_id).await?);
+    }
+
+    let mut secrets = BackupSecrets {
+        platform_configs: repos.platform_configs.list_platform_configs(None).await?,
+        credentials: Vec::new(),
+        config_secrets: Vec::new(),
+    };
+
+    let mut workspace_data = Vec::new();
+    for ws in &workspaces {
+        let scoped = repos.for_workspace(ws.workspace_id);
+        for cred in scoped.credentials.get_all_credentials().await? {
+            secrets.credentials.push((ws.workspace_id, cred));
+        }
+        for key in scoped.bot_config.list_secret_keys().await? {
+            if let Some(value) = scoped.bot_config.get_secret(&key).await? {
+                secrets.config_secrets.push((ws.workspace_id, key, value));
+            }
+        }
+
+        let mut bot_config = Vec::new();
+        for (key, value) in scoped.bot_config.list_all().await? {
+            let meta = scoped.bot_config.get_value_kv_meta(&key, &value).await?.and_then(|(_, meta)| meta);
+            bot_config.push(ConfigBackup { key, value, meta });
+        }
+
+        let mut commands = Vec::new();
+        let mut redeems = Vec::new();
+        for platform in ALL_PLATFORMS.iter().map(|p| p.to_string()) {
+            commands.extend(scoped.commands.list_commands(&platform).await?);
+            redeems.extend(scoped.redeems.list_redeems(&platform).await?);
+        }
+
+        workspace_data.push(WorkspaceBackup { workspace_id: ws.workspace_id, bot_config, commands, redeems });
+    }
+
+    let mut pipeline_backups = Vec::new();
+    for pipeline in pipelines.list_pipelines(false).await? {
+        let filters = pipelines.list_filters_for_pipeline(pipeline.pipeline_id).await?;
+        let actions = pipelines.list_actions_for_pipeline(pipeline.pipeline_id).await?;
+        pipeline_backups.push(PipelineBackup { pipeline, filters, actions });
+    }
+
+    let backup_key = format!("{}{}", BACKUP_KEY_PREFIX, URL_SAFE_NO_PAD.encode(generate_key()?));
+    let sealed_secrets = backup_encryptor(&backup_key)?.encrypt(&serde_json::to_string(&secrets)?)?;
+
+    let archive = BackupArchive {
+        format: BACKUP_FORMAT.to_string(),
+        format_version: BACKUP_FORMAT_VERSION,
+        schema_version: Database::schema_version(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        workspaces,
+        channel_routes,
+        users,
+        identities,
+        workspace_data,
+        pipelines: pipeline_backups,
+        sealed_secrets,
+    };
+
+    let mut counts = archive.counts();
+    counts.platform_configs = secrets.platform_configs.len();
+    counts.credentials = secrets.credentials.len();
+    Ok((archive, backup_key, counts))
+}
+
+/// Restore an archive into the current database. Existing rows win: workspaces
+/// and users that already exist (by id or name) are reused, and channel routes,
+/// commands, redeems, identities and pipelines that already exist are skipped. Bot config values and
+/// platform configs from the archive replace the current ones.
+///
+/// Without `backup_key`, everything except credentials and platform configs is restored.
+pub async fn import_backup(
+    repos: &BackendRepos,
+    pipelines: &dyn EventPipelineRepository,
+    archive: &BackupArchive,
+    backup_key: Option<&str>,
+) -> Result<ImportReport, Error> {
+    // Open the secrets first so a wrong key fails before anything is written
+    let secrets: Option<BackupSecrets> = match backup_key {
+        Some(key) => {
+            let json = backup_encryptor(key)?.decrypt(&archive.sealed_secrets)
+                .map_err(|_| Error::ValidationError("The backup key does not match this archive".into()))?;
+            Some(serde_json::from_str(&json)?)
+        }
+        None => None,
+    };
+
+    let mut report = ImportReport::default();
+
+    let mut workspace_map = HashMap::new();
+    for ws in &archive.workspaces {
+        let existing = match repos.workspaces.get_workspace(ws.workspace_id).await? {
+            Some(existing) => Some(existing),
+            None => repos.workspaces.get_workspace_by_name(&ws.name).await?,
+        };
+        let target = match existing {
+            Some(existing) => {
+                report.skipped.workspaces += 1;
+                existing.workspace_id
+            }
+            None => {
+                repos.workspaces.create_workspace(ws).await?;
+                report.restored.workspaces += 1;
+                ws.workspace_id
+            }
+        };
+        workspace_map.insert(ws.workspace_id, target);
+    }
+
+    let routed: Vec<(String, String)> = repos.workspaces.list_channels().await?
+        .into_iter()
+        .map(|r| (r.platform, r.channel))
+        .collect();
+    for route in &archive.channel_routes {
+        let Some(&ws) = workspace_map.get(&route.workspace_id) else { continue };
+        if routed.iter().any(|(p, c)| *p == route.platform && *c == route.channel) {
+            continue;
+        }
+        repos.workspaces.assign_channel(ws, &route.platform, &route.channel).await?;
+    }
+
+    let mut user_map = HashMap::new();
+    for user in &archive.users {
+        let existing = match repos.users.get(user.user_id).await? {
+            Some(existing) => Some(existing),
+            None => match &user.global_username {
+                Some(name) => repos.users.get_by_global_username(name).await?,
+                None => None,
+            },
+        };
+        let target = match existing {
+            Some(existing) => {
+                report.skipped.users += 1;
+                existing.user_id
+            }
+            None => match repos.users.create(user).await {
+                Ok(()) => {
+                    report.restored.users += 1;
+                    user.user_id
+                }
+                Err(e) => {
+                    report.warnings.push(format!("User {:?}: {}", user.global_username, e));
+                    continue;
+                }
+            },
+        };
+        user_map.insert(user.user_id, target);
+    }
+
+    for identity in &archive.identities {
+        let Some(&user_id) = user_map.get(&identity.user_id) else { continue };
+        if repos.identities.get_by_platform(identity.platform.clone(), &identity.platform_user_id).await?.is_some() {
+            report.skipped.identities += 1;
+            continue;
+        }
+        let identity = PlatformIdentity { user_id, ..identity.clone() };
+        match repos.identities.create(&identity).await {
+            Ok(()) => report.restored.identities += 1,
+            Err(e) => report.warnings.push(format!(
+                "Identity {} ({}): {}", identity.platform_username, identity.platform, e
+            )),
+        }
+    }
+
+    // Credential ids can change when a credential for the same account already exists
+    let mut credential_map = HashMap::new();
+    match secrets {
+        Some(secrets) => {
+            for pc in &secrets.platform_configs {
+                repos.platform_configs
+                    .upsert_platform_config(&pc.platform, pc.client_id.clone(), pc.client_secret.clone())
+                    .await?;
+                report.restored.platform_configs += 1;
+            }
+            for (ws_id, cred) in &secrets.credentials {
+                let (Some(&ws), Some(&user_id)) = (workspace_map.get(ws_id), user_map.get(&cred.user_id)) else {
+                    report.warnings.push(format!("Credential {} ({}): owner was not restored", cred.user_name, cred.platform));
+                    continue;
+                };
+                let scoped = repos.for_workspace(ws).credentials;
+                let cred = PlatformCredential { user_id, ..cred.clone() };
+                if let Err(e) = scoped.store_credentials(&cred).await {
+                    report.warnings.push(format!("Credential {} ({}): {}", cred.user_name, cred.platform, e));
+                    continue;
+                }
+                if let Some(stored) = scoped.get_credentials(&cred.platform, user_id).await? {
+                    credential_map.insert(cred.credential_id, stored.credential_id);
+                }
+                report.restored.credentials += 1;
+            }
+            for (ws_id, key, value) in &secrets.config_secrets {
+                let Some(&ws) = workspace_map.get(ws_id) else { continue };
+                repos.for_workspace(ws).bot_config.set_secret(key, value).await?;
+                report.restored.bot_config += 1;
+            }
+        }
+        None => report.warnings.push(
+            "No backup key given: credentials, platform client secrets and config secrets were not restored".to_string()
+        ),
+    }
+    let remap = |id: Option<Uuid>| id.and_then(|id| credential_map.get(&id).copied());
+
+    for data in &archive.workspace_data {
+        let Some(&ws) = workspace_map.get(&data.workspace_id) else { continue };
+        let scoped = repos.for_workspace(ws);
+
+        for entry in &data.bot_config {
+            scoped.bot_config.set_value_kv_meta(&entry.key, &entry.value, entry.meta.clone()).await?;
+            report.restored.bot_config += 1;
+        }
+
+        for cmd in &data.commands {
+            if scoped.commands.get_command_by_name(&cmd.platform, &cmd.command_name).await?.is_some() {
+                report.skipped.commands += 1;
+                continue;
+            }
+            let cmd = Command {
+                respond_with_credential: remap(cmd.respond_with_credential),
+                active_credential_id: remap(cmd.active_credential_id),
+                ..cmd.clone()
+            };
+            match scoped.commands.create_command(&cmd).await {
+                Ok(()) => report.restored.commands += 1,
+                Err(e) => report.warnings.push(format!("Command {} ({}): {}", cmd.command_name, cmd.platform, e)),
+            }
+        }
+
+        for rd in &data.redeems {
+            if scoped.redeems.get_redeem_by_reward_id(&rd.platform, &rd.reward_id).await?.is_some() {
+                report.skipped.redeems += 1;
+                continue;
+            }
+            let rd = Redeem { active_credential_id: remap(rd.active_credential_id), ..rd.clone() };
+            match scoped.redeems.create_redeem(&rd).await {
+                Ok(()) => report.restored.redeems += 1,
+                Err(e) => report.warnings.push(format!("Redeem {} ({}): {}", rd.reward_name, rd.platform, e)),
+            }
+        }
+    }
+
+    for backup in &archive.pipelines {
+        let p = &backup.pipeline;
+        if pipelines.get_pipeline_by_name(&p.name).await?.is_some() {
+            report.skipped.pipelines += 1;
+            continue;
+        }
+        if let Err(e) = restore_pipeline(pipelines, backup).await {
+            report.warnings.push(format!("Pipeline {}: {}", p.name, e));
+            continue;
+        }
+        report.restored.pipelines += 1;
+    }
+
+    Ok(report)
+}
+
+async fn restore_pipeline(pipelines: &dyn EventPipelineRepository, backup: &PipelineBackup) -> Result<(), Error> {
+    let p = &backup.pipeline;
+    let created = pipelines.create_pipeline(&CreatePipelineRequest {
+        name: p.name.clone(),
+        description: p.description.clone(),
+        enabled: p.enabled,
+        priority: p.priority,
+        stop_on_match: p.stop_on_match,
+        stop_on_error: p.stop_on_error,
+        tags: p.tags.clone(),
+        metadata: Some(p.metadata.clone()),
+    }).await?;
+
+    for f in &backup.filters {
+        pipelines.add_filter(created.pipeline_id, &CreateFilterRequest {
+            filter_type: f.filter_type.clone(),
+            filter_config: f.filter_config.clone(),
+            filter_order: f.filter_order,
+            is_negated: f.is_negated,
+            is_required: f.is_required,
+        }).await?;
+    }
+    for a in &backup.actions {
+        pipelines.add_action(created.pipeline_id, &CreateActionRequest {
+            action_type: a.action_type.clone(),
+            action_config: a.action_config.clone(),
+            action_order: a.action_order,
+            continue_on_error: a.continue_on_error,
+            is_async: a.is_async,
+            timeout_ms: a.timeout_ms,
+            retry_count: a.retry_count,
+            retry_delay_ms: a.retry_delay_ms,
+            condition_type: a.condition_type.clone(),
+            condition_config: a.condition_config.clone(),
+        }).await?;
+    }
+    Ok(())
+}
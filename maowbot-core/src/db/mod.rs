@@ -5,6 +5,87 @@ use sqlx::{Pool, Postgres};
 use crate::Error;
 use anyhow::Result;
 
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod transfer;
+pub mod backup;
+
+/// Which backend a database URL points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbBackend {
+    Postgres,
+    Sqlite,
+}
+
+impl DbBackend {
+    /// Picks the backend from the URL scheme (`postgres://`, `postgresql://` or `sqlite://`).
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let scheme = url.split_once(':').map(|(scheme, _)| scheme.to_ascii_lowercase());
+        match scheme.as_deref() {
+            Some("postgres") | Some("postgresql") => Ok(Self::Postgres),
+            Some("sqlite") => Ok(Self::Sqlite),
+            _ => Err(Error::Parse(format!(
+                "Unsupported database URL '{}': expected postgres://... or sqlite://...", url
+            ))),
+        }
+    }
+}
+
+/// The database the server runs on. Backend-specific upkeep (partitions,
+/// snapshots, VACUUM) matches on it; everything else goes through the
+/// repositories built from it.
+#[derive(Clone)]
+pub enum DbConnection {
+    Postgres(Database),
+    #[cfg(feature = "sqlite")]
+    Sqlite(sqlite::SqliteDatabase),
+    /// `--memory-db`: the in-memory SQLite database behind
+    /// `Repositories::memory`. Nothing in it outlives the process.
+    #[cfg(feature = "memory")]
+    Memory(sqlite::SqliteDatabase),
+}
+
+impl DbConnection {
+    pub fn backend_name(&self) -> &'static str {
+        match self {
+            Self::Postgres(_) => "postgres",
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(_) => "sqlite",
+            #[cfg(feature = "memory")]
+            Self::Memory(_) => "memory",
+        }
+    }
+
+    /// The Postgres database, for the Postgres-only maintenance.
+    pub fn postgres(&self) -> Option<&Database> {
+        match self {
+            Self::Postgres(db) => Some(db),
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(_) => None,
+            #[cfg(feature = "memory")]
+            Self::Memory(_) => None,
+        }
+    }
+
+    /// Round trip to the database.
+    pub async fn ping(&self) -> Result<(), Error> {
+        match self {
+            Self::Postgres(db) => {
+                sqlx::query("SELECT 1").execute(db.pool()).await?;
+            }
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(db) => {
+                sqlx::query("SELECT 1").execute(db.pool()).await?;
+            }
+            #[cfg(feature = "memory")]
+            Self::Memory(db) => {
+                sqlx::query("SELECT 1").execute(db.pool()).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Our Database struct now uses a Pool<Postgres>.
 #[derive(Clone)] // <-- Added derive for Clone
 pub struct Database {
@@ -36,6 +117,48 @@ impl Database {
         Ok(())
     }
 
+    /// Version of the newest migration this build applies; backups are tagged with it.
+    pub fn schema_version() -> i64 {
+        sqlx::migrate!("../migrations").migrations.iter().map(|m| m.version).max().unwrap_or(0)
+    }
+
+    /// Versions of the migrations `migrate` would apply, and how many are already applied.
+    pub async fn migration_status(&self) -> Result<(Vec<i64>, usize), Error> {
+        let has_table: Option<String> = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations')::text")
+            .fetch_one(&self.pool)
+            .await?;
+        let applied: Vec<i64> = if has_table.is_some() {
+            sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            Vec::new()
+        };
+        let pending = sqlx::migrate!("../migrations").migrations.iter()
+            .map(|m| m.version)
+            .filter(|v| !applied.contains(v))
+            .collect();
+        Ok((pending, applied.len()))
+    }
+
+    /// Drops every table (`--nuke-database-and-start-fresh`); `migrate` rebuilds them.
+    pub async fn drop_all_tables(&self) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            DO $$ DECLARE
+                r RECORD;
+            BEGIN
+                FOR r IN (SELECT tablename FROM pg_tables WHERE schemaname = 'public') LOOP
+                    EXECUTE 'DROP TABLE IF EXISTS ' || quote_ident(r.tablename) || ' CASCADE';
+                END LOOP;
+            END $$;
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     pub fn pool(&self) -> &Pool<Postgres> {
         &self.pool
     }
@@ -43,4 +166,19 @@ impl Database {
     pub fn from_pool(pool: Pool<Postgres>) -> Self {
         Self { pool }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DbBackend;
+
+    #[test]
+    fn test_backend_from_url() {
+        assert_eq!(DbBackend::from_url("postgres://maow@localhost:5432/maowbot").unwrap(), DbBackend::Postgres);
+        assert_eq!(DbBackend::from_url("postgresql://localhost/maowbot").unwrap(), DbBackend::Postgres);
+        assert_eq!(DbBackend::from_url("sqlite://maowbot.db").unwrap(), DbBackend::Sqlite);
+        assert_eq!(DbBackend::from_url("sqlite::memory:").unwrap(), DbBackend::Sqlite);
+        assert!(DbBackend::from_url("mysql://localhost/maowbot").is_err());
+        assert!(DbBackend::from_url("maowbot.db").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+// maowbot-core/src/db/sqlite.rs
+//
+// Embedded SQLite database for small setups: the server runs on it with
+// `--db sqlite://path/to/maowbot.db`, and `--mode migrate-db` copies to and
+// from it. Only compiled with the `sqlite` feature.
+
+use std::path::Path;
+use std::str::FromStr;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Pool, Sqlite};
+use tracing::{debug, info};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteDatabase {
+    /// Open (or create) the database file named by a `sqlite://` URL.
+    pub async fn new(database_url: &str) -> Result<Self, Error> {
+        let options = SqliteConnectOptions::from_str(database_url)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .foreign_keys(true);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await?;
+
+        info!("Opened SQLite database at {}", database_url);
+        Ok(Self { pool })
+    }
+
+    /// A private in-memory database that lives as long as the pool. It has a
+    /// single connection, since each connection to `:memory:` is its own database.
+    pub async fn in_memory() -> Result<Self, Error> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?
+            .foreign_keys(true);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Run migrations in the `migrations_sqlite/` folder.
+    pub async fn migrate(&self) -> Result<(), Error> {
+        debug!("Applying SQLite migrations...");
+        sqlx::migrate!("../migrations_sqlite").run(&self.pool).await?;
+        info!("SQLite migrations applied.");
+        Ok(())
+    }
+
+    /// Versions of the migrations `migrate` would apply, and how many are already applied.
+    pub async fn migration_status(&self) -> Result<(Vec<i64>, usize), Error> {
+        let has_table: Option<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'"
+        )
+            .fetch_optional(&self.pool)
+            .await?;
+        let applied: Vec<i64> = if has_table.is_some() {
+            sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            Vec::new()
+        };
+        let pending = sqlx::migrate!("../migrations_sqlite").migrations.iter()
+            .map(|m| m.version)
+            .filter(|v| !applied.contains(v))
+            .collect();
+        Ok((pending, applied.len()))
+    }
+
+    /// Drops every table (`--nuke-database-and-start-fresh`); `migrate` rebuilds them.
+    pub async fn drop_all_tables(&self) -> Result<(), Error> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'"
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+        for table in &tables {
+            sqlx::query(&format!("DROP TABLE IF EXISTS \"{}\"", table.replace('"', "\"\"")))
+                .execute(&mut *conn)
+                .await?;
+        }
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+        Ok(())
+    }
+
+    /// Writes a consistent copy of the database to `dest` (`VACUUM INTO`).
+    pub async fn copy_to(&self, dest: &Path) -> Result<(), Error> {
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        sqlx::query("VACUUM INTO ?1")
+            .bind(dest.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub fn pool(&self) -> &Pool<Sqlite> {
+        &self.pool
+    }
+
+    pub fn from_pool(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
@@ -0,0 +1,744 @@
+// maowbot-core/src/db/transfer.rs
+//
+// Copies data between database backends (Postgres <-> SQLite) through the
+// repository traits, so each side reads and writes its own column types.
+// Used by `maowbot --mode migrate-db`.
+//
+// Every step is an upsert on the row's natural key (a user's id, a command's
+// platform and name, a provider's name, ...): rows the target already has,
+// like the builtin commands and AI providers the Postgres migrations seed,
+// are updated from the source and keep their ids, and references to them are
+// pointed at those ids. Running the copy twice gives the same result.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use maowbot_common::models::ai::{AiAction, AiAgent, AiCredential, AiModel, AiProvider, AiSystemPrompt, AiTrigger};
+use maowbot_common::models::alert_rule::AlertRule;
+use maowbot_common::models::command::Command;
+use maowbot_common::models::midi_mapping::MidiMapping;
+use maowbot_common::models::moderation_rule::ModerationRule;
+use maowbot_common::models::platform::{Platform, PlatformIdentity};
+use maowbot_common::models::redeem::Redeem;
+use maowbot_common::models::redeem_schedule::RedeemSchedule;
+use maowbot_common::models::responder::Responder;
+use maowbot_common::traits::repository_traits::*;
+
+use crate::crypto::Encryptor;
+use crate::db::{Database, DbBackend};
+use crate::repositories::postgres::autostart::AutostartRepository;
+use crate::repositories::Repositories;
+pub use crate::repositories::WorkspaceRepos;
+use crate::Error;
+
+/// Platforms commands and redeems can be stored under.
+pub(crate) const ALL_PLATFORMS: [Platform; 7] = [
+    Platform::Twitch,
+    Platform::TwitchIRC,
+    Platform::TwitchEventSub,
+    Platform::Discord,
+    Platform::VRChat,
+    Platform::OBS,
+    Platform::Kick,
+];
+
+/// `Repositories` fields `copy_all` leaves behind: history, usage and runtime
+/// state, and settings tied to one machine's devices and connections.
+/// `--mode migrate-db` refuses to run without `--partial` while this isn't empty.
+pub const NOT_COPIED: &[&str] = &[
+    "user_analysis",
+    "user_notes",
+    "analytics",
+    "chat_archive",
+    "command_usage",
+    "redeem_usage",
+    "redeem_approvals",
+    "discord",
+    "obs",
+    "drip",
+    "osc_toggles",
+    "pipelines",
+    "ai_memories",
+    "donations",
+    "stream_markers",
+    "stream_sessions",
+    "giveaways",
+    "protection_incidents",
+    "emote_stats",
+    "clips",
+    "watchtime",
+    "milestones",
+    "retention",
+    "ui_journal",
+    "analysis_runs",
+    "exports",
+    "audit log",
+];
+
+/// The repositories one backend offers to the transfer.
+pub struct BackendRepos {
+    pub workspaces: Arc<dyn WorkspaceRepository>,
+    pub users: Arc<dyn UserRepo + Send + Sync>,
+    pub identities: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+    pub platform_configs: Arc<dyn PlatformConfigRepository + Send + Sync>,
+    pub api_tokens: Arc<dyn ApiTokenRepository>,
+    pub autostart: Arc<dyn AutostartRepository + Send + Sync>,
+    pub redeem_schedules: Arc<dyn RedeemScheduleRepository>,
+    pub midi_mappings: Arc<dyn MidiMappingRepository + Send + Sync>,
+    pub alert_rules: Arc<dyn AlertRuleRepository>,
+    pub responders: Arc<dyn ResponderRepository>,
+    pub moderation_rules: Arc<dyn ModerationRuleRepository>,
+    pub localization: Arc<dyn LocalizationRepository>,
+    pub ai_providers: Arc<dyn AiProviderRepository + Send + Sync>,
+    pub ai_credentials: Arc<dyn AiCredentialRepository + Send + Sync>,
+    pub ai_models: Arc<dyn AiModelRepository + Send + Sync>,
+    pub ai_prompts: Arc<dyn AiSystemPromptRepository + Send + Sync>,
+    pub ai_agents: Arc<dyn AiAgentRepository + Send + Sync>,
+    pub ai_actions: Arc<dyn AiActionRepository + Send + Sync>,
+    pub ai_triggers: Arc<dyn AiTriggerRepository + Send + Sync>,
+    scoped: Box<dyn Fn(Uuid) -> WorkspaceRepos + Send + Sync>,
+}
+
+impl BackendRepos {
+    /// Connects to `url` (postgres:// or sqlite://) and applies that backend's migrations.
+    pub async fn connect(url: &str, encryptor: Encryptor) -> Result<Self, Error> {
+        match DbBackend::from_url(url)? {
+            DbBackend::Postgres => {
+                let db = Database::new(url).await?;
+                db.migrate().await?;
+                Ok(Self::postgres(&db, encryptor))
+            }
+            #[cfg(feature = "sqlite")]
+            DbBackend::Sqlite => {
+                let db = crate::db::sqlite::SqliteDatabase::new(url).await?;
+                db.migrate().await?;
+                Ok(Self::sqlite(&db, encryptor))
+            }
+            #[cfg(not(feature = "sqlite"))]
+            DbBackend::Sqlite => Err(Error::Internal(
+                "This build has no SQLite support; rebuild with `--features sqlite`".into()
+            )),
+        }
+    }
+
+    pub fn postgres(db: &Database, encryptor: Encryptor) -> Self {
+        Self::from_repositories(&Repositories::postgres(db, encryptor))
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(db: &crate::db::sqlite::SqliteDatabase, encryptor: Encryptor) -> Self {
+        Self::from_repositories(&Repositories::sqlite(db, encryptor))
+    }
+
+    /// The subset of a running server's repositories that backups and transfers use.
+    pub fn from_repositories(repos: &Repositories) -> Self {
+        let scoped = repos.clone();
+        Self {
+            workspaces: repos.workspaces.clone(),
+            users: repos.users.clone(),
+            identities: repos.identities.clone(),
+            platform_configs: repos.platform_configs.clone(),
+            api_tokens: repos.api_tokens.clone(),
+            autostart: repos.autostart.clone(),
+            redeem_schedules: repos.redeem_schedules.clone(),
+            midi_mappings: repos.midi_mappings.clone(),
+            alert_rules: repos.alert_rules.clone(),
+            responders: repos.responders.clone(),
+            moderation_rules: repos.moderation_rules.clone(),
+            localization: repos.localization.clone(),
+            ai_providers: repos.ai_providers.clone(),
+            ai_credentials: repos.ai_credentials.clone(),
+            ai_models: repos.ai_models.clone(),
+            ai_prompts: repos.ai_prompts.clone(),
+            ai_agents: repos.ai_agents.clone(),
+            ai_actions: repos.ai_actions.clone(),
+            ai_triggers: repos.ai_triggers.clone(),
+            scoped: Box::new(move |ws| scoped.for_workspace(ws)),
+        }
+    }
+
+    pub fn for_workspace(&self, workspace_id: Uuid) -> WorkspaceRepos {
+        (self.scoped)(workspace_id)
+    }
+}
+
+/// How many rows of each kind were copied (created or updated).
+#[derive(Debug, Default, Clone)]
+pub struct TransferReport {
+    pub workspaces: usize,
+    pub channel_routes: usize,
+    pub users: usize,
+    pub identities: usize,
+    pub platform_configs: usize,
+    pub credentials: usize,
+    pub bot_config: usize,
+    pub commands: usize,
+    pub redeems: usize,
+    pub redeem_schedules: usize,
+    pub api_tokens: usize,
+    pub autostart: usize,
+    pub midi_mappings: usize,
+    pub alert_rules: usize,
+    pub responders: usize,
+    pub moderation_rules: usize,
+    pub localization: usize,
+    pub ai: usize,
+}
+
+impl fmt::Display for TransferReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  workspaces:       {}", self.workspaces)?;
+        writeln!(f, "  channel routes:   {}", self.channel_routes)?;
+        writeln!(f, "  users:            {}", self.users)?;
+        writeln!(f, "  identities:       {}", self.identities)?;
+        writeln!(f, "  platform configs: {}", self.platform_configs)?;
+        writeln!(f, "  credentials:      {}", self.credentials)?;
+        writeln!(f, "  bot config:       {}", self.bot_config)?;
+        writeln!(f, "  commands:         {}", self.commands)?;
+        writeln!(f, "  redeems:          {}", self.redeems)?;
+        writeln!(f, "  redeem schedules: {}", self.redeem_schedules)?;
+        writeln!(f, "  api tokens:       {}", self.api_tokens)?;
+        writeln!(f, "  autostart:        {}", self.autostart)?;
+        writeln!(f, "  midi mappings:    {}", self.midi_mappings)?;
+        writeln!(f, "  alert rules:      {}", self.alert_rules)?;
+        writeln!(f, "  responders:       {}", self.responders)?;
+        writeln!(f, "  moderation rules: {}", self.moderation_rules)?;
+        writeln!(f, "  localization:     {}", self.localization)?;
+        write!(f, "  ai settings:      {}", self.ai)
+    }
+}
+
+/// Copies everything but `NOT_COPIED` from `from` into `to`, upserting rows
+/// `to` already has (see the module comment). API tokens `to` already has by
+/// name are left alone, so their secrets keep working.
+pub async fn copy_all(from: &BackendRepos, to: &BackendRepos) -> Result<TransferReport, Error> {
+    let mut report = TransferReport::default();
+
+    // Workspaces first: everything below is scoped to one. Both schemas seed 'default'.
+    let workspaces = from.workspaces.list_workspaces().await?;
+    for ws in &workspaces {
+        if to.workspaces.get_workspace(ws.workspace_id).await?.is_none() {
+            to.workspaces.create_workspace(ws).await?;
+            report.workspaces += 1;
+        }
+    }
+    for route in from.workspaces.list_channels().await? {
+        to.workspaces.assign_channel(route.workspace_id, &route.platform, &route.channel).await?;
+        report.channel_routes += 1;
+    }
+
+    // Users and identities before credentials, which reference users
+    for user in from.users.list_all().await? {
+        if to.users.get(user.user_id).await?.is_some() {
+            to.users.update(&user).await?;
+        } else {
+            to.users.create(&user).await?;
+        }
+        report.users += 1;
+        for identity in from.identities.get_all_for_user(user.user_id).await? {
+            match to.identities.get_by_platform(identity.platform.clone(), &identity.platform_user_id).await? {
+                Some(existing) => {
+                    to.identities.update(&PlatformIdentity {
+                        platform_identity_id: existing.platform_identity_id,
+                        ..identity
+                    }).await?
+                }
+                None => to.identities.create(&identity).await?,
+            }
+            report.identities += 1;
+        }
+    }
+
+    for pc in from.platform_configs.list_platform_configs(None).await? {
+        to.platform_configs
+            .upsert_platform_config(&pc.platform, pc.client_id.clone(), pc.client_secret.clone())
+            .await?;
+        report.platform_configs += 1;
+    }
+
+    // Source redeem id -> target redeem id, for the schedules
+    let mut redeem_ids = HashMap::new();
+    for ws in &workspaces {
+        let src = from.for_workspace(ws.workspace_id);
+        let dst = to.for_workspace(ws.workspace_id);
+
+        // Credentials before commands/redeems, which reference them
+        for cred in src.credentials.get_all_credentials().await? {
+            dst.credentials.store_credentials(&cred).await?;
+            report.credentials += 1;
+        }
+
+        for (key, value) in src.bot_config.list_all().await? {
+            let meta = src.bot_config.get_value_kv_meta(&key, &value).await?.and_then(|(_, meta)| meta);
+            dst.bot_config.set_value_kv_meta(&key, &value, meta).await?;
+            report.bot_config += 1;
+        }
+        for key in src.bot_config.list_secret_keys().await? {
+            if let Some(value) = src.bot_config.get_secret(&key).await? {
+                dst.bot_config.set_secret(&key, &value).await?;
+                report.bot_config += 1;
+            }
+        }
+
+        for platform in ALL_PLATFORMS.iter().map(|p| p.to_string()) {
+            for cmd in src.commands.list_commands(&platform).await? {
+                match dst.commands.get_command_by_name(&platform, &cmd.command_name).await? {
+                    Some(existing) => {
+                        dst.commands.update_command(&Command { command_id: existing.command_id, ..cmd }).await?
+                    }
+                    None => dst.commands.create_command(&cmd).await?,
+                }
+                report.commands += 1;
+            }
+            for rd in src.redeems.list_redeems(&platform).await? {
+                let target_id = match dst.redeems.get_redeem_by_reward_id(&platform, &rd.reward_id).await? {
+                    Some(existing) => {
+                        dst.redeems.update_redeem(&Redeem { redeem_id: existing.redeem_id, ..rd.clone() }).await?;
+                        existing.redeem_id
+                    }
+                    None => {
+                        dst.redeems.create_redeem(&rd).await?;
+                        rd.redeem_id
+                    }
+                };
+                redeem_ids.insert(rd.redeem_id, target_id);
+                report.redeems += 1;
+            }
+        }
+    }
+
+    let existing_schedules = to.redeem_schedules.list_schedules().await?;
+    for schedule in from.redeem_schedules.list_schedules().await? {
+        let Some(&redeem_id) = redeem_ids.get(&schedule.redeem_id) else { continue };
+        let schedule = RedeemSchedule { redeem_id, ..schedule };
+        match existing_schedules.iter().find(|s| s.redeem_id == redeem_id && s.name == schedule.name) {
+            Some(existing) => {
+                to.redeem_schedules
+                    .update_schedule(&RedeemSchedule { schedule_id: existing.schedule_id, ..schedule })
+                    .await?
+            }
+            None => to.redeem_schedules.create_schedule(&schedule).await?,
+        }
+        report.redeem_schedules += 1;
+    }
+
+    for token in from.api_tokens.list_tokens().await? {
+        if to.api_tokens.get_token_by_name(&token.name).await?.is_none() {
+            to.api_tokens.create_token(&token).await?;
+            report.api_tokens += 1;
+        }
+    }
+
+    copy_settings(from, to, &mut report).await?;
+    copy_ai(from, to, &mut report).await?;
+    Ok(report)
+}
+
+/// Autostart, MIDI mappings, alert rules, responders, moderation rules and localization.
+async fn copy_settings(from: &BackendRepos, to: &BackendRepos, report: &mut TransferReport) -> Result<(), Error> {
+    for entry in from.autostart.get_all_entries().await? {
+        to.autostart.set_autostart(&entry.platform, &entry.account_name, entry.enabled).await?;
+        report.autostart += 1;
+    }
+
+    for mapping in from.midi_mappings.list_mappings().await? {
+        match to.midi_mappings.get_mapping_by_name(&mapping.name).await? {
+            Some(existing) => {
+                to.midi_mappings.update_mapping(&MidiMapping { mapping_id: existing.mapping_id, ..mapping }).await?
+            }
+            None => to.midi_mappings.create_mapping(&mapping).await?,
+        }
+        report.midi_mappings += 1;
+    }
+
+    for rule in from.alert_rules.list_rules().await? {
+        match to.alert_rules.get_rule_by_name(&rule.name).await? {
+            Some(existing) => to.alert_rules.update_rule(&AlertRule { rule_id: existing.rule_id, ..rule }).await?,
+            None => to.alert_rules.create_rule(&rule).await?,
+        }
+        report.alert_rules += 1;
+    }
+
+    for responder in from.responders.list_responders().await? {
+        match to.responders.get_responder_by_name(&responder.name).await? {
+            Some(existing) => {
+                to.responders
+                    .update_responder(&Responder { responder_id: existing.responder_id, ..responder })
+                    .await?
+            }
+            None => to.responders.create_responder(&responder).await?,
+        }
+        report.responders += 1;
+    }
+
+    for rule in from.moderation_rules.list_rules().await? {
+        match to.moderation_rules.get_rule_by_name(&rule.name).await? {
+            Some(existing) => {
+                to.moderation_rules.update_rule(&ModerationRule { rule_id: existing.rule_id, ..rule }).await?
+            }
+            None => to.moderation_rules.create_rule(&rule).await?,
+        }
+        report.moderation_rules += 1;
+    }
+
+    let mut by_language: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for string in from.localization.list_strings().await? {
+        by_language.entry(string.language).or_default().push((string.key, string.template));
+    }
+    for (language, strings) in by_language {
+        to.localization.upsert_strings(&language, &strings).await?;
+        report.localization += strings.len();
+    }
+    for channel in from.localization.list_channel_languages().await? {
+        to.localization.set_channel_language(&channel).await?;
+        report.localization += 1;
+    }
+    Ok(())
+}
+
+/// AI providers, credentials, models, prompts, agents, actions and triggers.
+/// Both schemas seed some of these under their own ids, so references are
+/// mapped to the target's ids as rows are matched by name.
+async fn copy_ai(from: &BackendRepos, to: &BackendRepos, report: &mut TransferReport) -> Result<(), Error> {
+    let mut provider_ids = HashMap::new();
+    let mut model_ids = HashMap::new();
+    for provider in from.ai_providers.list_providers().await? {
+        let target_id = match to.ai_providers.get_provider_by_name(&provider.name).await? {
+            Some(existing) => {
+                to.ai_providers
+                    .update_provider(&AiProvider { provider_id: existing.provider_id, ..provider.clone() })
+                    .await?;
+                existing.provider_id
+            }
+            None => {
+                to.ai_providers.create_provider(&provider).await?;
+                provider.provider_id
+            }
+        };
+        provider_ids.insert(provider.provider_id, target_id);
+        report.ai += 1;
+
+        let existing_credentials = to.ai_credentials.list_credentials_for_provider(target_id).await?;
+        for cred in from.ai_credentials.list_credentials_for_provider(provider.provider_id).await? {
+            let is_default = cred.is_default;
+            let cred = AiCredential { provider_id: target_id, is_default: false, ..cred };
+            let credential_id = match existing_credentials.iter()
+                .find(|c| c.credential_id == cred.credential_id || c.api_key == cred.api_key)
+            {
+                Some(existing) => {
+                    let cred = AiCredential { credential_id: existing.credential_id, is_default: existing.is_default, ..cred };
+                    to.ai_credentials.update_credential(&cred).await?;
+                    cred.credential_id
+                }
+                None => {
+                    to.ai_credentials.create_credential(&cred).await?;
+                    cred.credential_id
+                }
+            };
+            if is_default {
+                to.ai_credentials.set_default_credential(credential_id).await?;
+            }
+            report.ai += 1;
+        }
+
+        for model in from.ai_models.list_models_for_provider(provider.provider_id).await? {
+            let is_default = model.is_default;
+            let model_id = model.model_id;
+            let model = AiModel { provider_id: target_id, is_default: false, ..model };
+            let target_model = match to.ai_models.get_model_by_name(target_id, &model.name).await? {
+                Some(existing) => {
+                    let model = AiModel { model_id: existing.model_id, is_default: existing.is_default, ..model };
+                    to.ai_models.update_model(&model).await?;
+                    model.model_id
+                }
+                None => {
+                    to.ai_models.create_model(&model).await?;
+                    model.model_id
+                }
+            };
+            if is_default {
+                to.ai_models.set_default_model(target_model).await?;
+            }
+            model_ids.insert(model_id, target_model);
+            report.ai += 1;
+        }
+    }
+
+    for prompt in from.ai_prompts.list_prompts().await? {
+        let is_default = prompt.is_default;
+        let prompt = AiSystemPrompt { is_default: false, ..prompt };
+        let prompt_id = match to.ai_prompts.get_prompt_by_name(&prompt.name).await? {
+            Some(existing) => {
+                let prompt = AiSystemPrompt { prompt_id: existing.prompt_id, is_default: existing.is_default, ..prompt };
+                to.ai_prompts.update_prompt(&prompt).await?;
+                prompt.prompt_id
+            }
+            None => {
+                to.ai_prompts.create_prompt(&prompt).await?;
+                prompt.prompt_id
+            }
+        };
+        if is_default {
+            to.ai_prompts.set_default_prompt(prompt_id).await?;
+        }
+        report.ai += 1;
+    }
+
+    let mut agent_ids = HashMap::new();
+    for agent in from.ai_agents.list_agents().await? {
+        let source_id = agent.agent_id;
+        let Some(&model_id) = model_ids.get(&agent.model_id) else { continue };
+        let agent = AiAgent { model_id, ..agent };
+        let target_id = match to.ai_agents.get_agent_by_name(&agent.name).await? {
+            Some(existing) => {
+                to.ai_agents.update_agent(&AiAgent { agent_id: existing.agent_id, ..agent }).await?;
+                existing.agent_id
+            }
+            None => {
+                to.ai_agents.create_agent(&agent).await?;
+                agent.agent_id
+            }
+        };
+        agent_ids.insert(source_id, target_id);
+        report.ai += 1;
+
+        for action in from.ai_actions.list_actions_for_agent(source_id).await? {
+            let action = AiAction { agent_id: target_id, ..action };
+            match to.ai_actions.get_action_by_name(target_id, &action.name).await? {
+                Some(existing) => {
+                    to.ai_actions.update_action(&AiAction { action_id: existing.action_id, ..action }).await?
+                }
+                None => to.ai_actions.create_action(&action).await?,
+            }
+            report.ai += 1;
+        }
+    }
+
+    for trigger in from.ai_triggers.list_triggers().await? {
+        let trigger = AiTrigger {
+            model_id: trigger.model_id.and_then(|id| model_ids.get(&id).copied()),
+            agent_id: trigger.agent_id.and_then(|id| agent_ids.get(&id).copied()),
+            ..trigger
+        };
+        match to.ai_triggers.get_trigger_by_pattern(&trigger.pattern).await? {
+            Some(existing) => {
+                to.ai_triggers.update_trigger(&AiTrigger { trigger_id: existing.trigger_id, ..trigger }).await?
+            }
+            None => to.ai_triggers.create_trigger(&trigger).await?,
+        }
+        report.ai += 1;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::{Connection, PgConnection};
+    use maowbot_common::models::credential::CredentialType;
+    use maowbot_common::models::platform::PlatformCredential;
+    use maowbot_common::models::redeem_schedule::{ScheduleAction, ScheduleCondition};
+    use maowbot_common::models::user::User;
+    use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+    use crate::db::sqlite::SqliteDatabase;
+
+    fn encryptor() -> Encryptor {
+        Encryptor::new(&[3u8; 32]).unwrap()
+    }
+
+    async fn sqlite() -> BackendRepos {
+        let db = SqliteDatabase::in_memory().await.unwrap();
+        db.migrate().await.unwrap();
+        BackendRepos::sqlite(&db, encryptor())
+    }
+
+    fn command(name: &str, response: &str) -> Command {
+        let now = Utc::now();
+        Command {
+            command_id: Uuid::new_v4(),
+            platform: "twitch".to_string(),
+            command_name: name.to_string(),
+            min_role: "viewer".to_string(),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            cooldown_seconds: 0,
+            cooldown_warnonce: false,
+            respond_with_credential: None,
+            stream_online_only: false,
+            stream_offline_only: false,
+            active_credential_id: None,
+            user_cooldown_seconds: 0,
+            role_cooldowns: Default::default(),
+            cooldown_bypass_mods: false,
+            reply_privately: false,
+            reply_to_message: false,
+            response: Some(response.to_string()),
+        }
+    }
+
+    fn redeem(reward_id: &str, cost: i32) -> Redeem {
+        Redeem {
+            redeem_id: Uuid::new_v4(),
+            platform: "twitch".to_string(),
+            reward_id: reward_id.to_string(),
+            reward_name: format!("Reward {}", reward_id),
+            cost,
+            is_active: true,
+            dynamic_pricing: false,
+            active_offline: false,
+            is_managed: false,
+            plugin_name: None,
+            command_name: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            active_credential_id: None,
+            is_input_required: false,
+            redeem_prompt_text: None,
+            requires_approval: false,
+        }
+    }
+
+    /// A SQLite database with rows that collide with what the Postgres migrations seed.
+    async fn seed(repos: &BackendRepos) -> User {
+        let now = Utc::now();
+        let user = User {
+            user_id: Uuid::new_v4(),
+            global_username: Some("maow".to_string()),
+            created_at: now,
+            last_seen: now,
+            is_active: true,
+        };
+        repos.users.create(&user).await.unwrap();
+        repos.identities.create(&PlatformIdentity {
+            platform_identity_id: Uuid::new_v4(),
+            user_id: user.user_id,
+            platform: Platform::Twitch,
+            platform_user_id: "1234".to_string(),
+            platform_username: "maow".to_string(),
+            platform_display_name: Some("Maow".to_string()),
+            platform_roles: vec!["broadcaster".to_string()],
+            platform_data: serde_json::json!({}),
+            created_at: now,
+            last_updated: now,
+        }).await.unwrap();
+
+        let ws = repos.for_workspace(DEFAULT_WORKSPACE_ID);
+        ws.credentials.store_credentials(&PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::Twitch,
+            platform_id: Some("1234".to_string()),
+            credential_type: CredentialType::OAuth2,
+            user_id: user.user_id,
+            user_name: "maow".to_string(),
+            primary_token: "token".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            additional_data: None,
+            expires_at: None,
+            created_at: now,
+            updated_at: now,
+            is_bot: false,
+            is_teammate: false,
+            is_broadcaster: true,
+        }).await.unwrap();
+        ws.bot_config.set_value("greeting", "hi").await.unwrap();
+        ws.bot_config.set_secret("webhook.token", "s3cret").await.unwrap();
+
+        // 'ping' and 'builtin_cute' are seeded by the Postgres migrations
+        ws.commands.create_command(&command("ping", "custom pong")).await.unwrap();
+        ws.commands.create_command(&command("hug", "hugs {user}")).await.unwrap();
+        let cute = redeem("builtin_cute", 42);
+        ws.redeems.create_redeem(&cute).await.unwrap();
+        repos.redeem_schedules.create_schedule(&RedeemSchedule {
+            schedule_id: Uuid::new_v4(),
+            redeem_id: cute.redeem_id,
+            name: "hype".to_string(),
+            condition: ScheduleCondition::HypeTrain,
+            condition_value: String::new(),
+            action: ScheduleAction::SetCost,
+            amount: Some(1.0),
+            priority: 0,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }).await.unwrap();
+
+        // Both schemas seed the providers, under their own ids
+        let provider = repos.ai_providers.list_providers().await.unwrap().remove(0);
+        repos.ai_credentials
+            .create_credential(&AiCredential::new(provider.provider_id, "sk-maow", None, true, None))
+            .await
+            .unwrap();
+        user
+    }
+
+    /// What `seed` wrote, as it should read back from any copy of it.
+    async fn check(repos: &BackendRepos, user: &User) {
+        assert_eq!(repos.users.list_all().await.unwrap().len(), 1);
+        assert!(repos.users.get(user.user_id).await.unwrap().is_some());
+        assert_eq!(repos.identities.get_all_for_user(user.user_id).await.unwrap().len(), 1);
+
+        let ws = repos.for_workspace(DEFAULT_WORKSPACE_ID);
+        let cred = ws.credentials.get_credentials(&Platform::Twitch, user.user_id).await.unwrap().unwrap();
+        assert_eq!(cred.primary_token, "token");
+        assert_eq!(ws.bot_config.get_value("greeting").await.unwrap().as_deref(), Some("hi"));
+        assert_eq!(ws.bot_config.get_secret("webhook.token").await.unwrap().as_deref(), Some("s3cret"));
+
+        let commands = ws.commands.list_commands("twitch").await.unwrap();
+        assert_eq!(commands.iter().filter(|c| c.command_name == "ping").count(), 1);
+        let ping = ws.commands.get_command_by_name("twitch", "ping").await.unwrap().unwrap();
+        assert_eq!(ping.response.as_deref(), Some("custom pong"));
+        assert!(ws.commands.get_command_by_name("twitch", "hug").await.unwrap().is_some());
+
+        let cute = ws.redeems.get_redeem_by_reward_id("twitch", "builtin_cute").await.unwrap().unwrap();
+        assert_eq!(cute.cost, 42);
+        let schedules = repos.redeem_schedules.list_schedules().await.unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].redeem_id, cute.redeem_id);
+
+        let keys: Vec<_> = repos.ai_credentials.list_credentials().await.unwrap()
+            .into_iter().map(|c| c.api_key).collect();
+        assert_eq!(keys, ["sk-maow"]);
+    }
+
+    async fn round_trip(pg_url: &str) -> Result<(), Error> {
+        let source = sqlite().await;
+        let user = seed(&source).await;
+
+        let postgres = BackendRepos::connect(pg_url, encryptor()).await?;
+        copy_all(&source, &postgres).await?;
+        // Again, over what the first run copied
+        copy_all(&source, &postgres).await?;
+        check(&postgres, &user).await;
+        // The seeded builtins are still there
+        let ws = postgres.for_workspace(DEFAULT_WORKSPACE_ID);
+        assert!(ws.commands.get_command_by_name("twitch", "uptime").await?.is_some());
+
+        let back = sqlite().await;
+        copy_all(&postgres, &back).await?;
+        copy_all(&postgres, &back).await?;
+        check(&back, &user).await;
+        Ok(())
+    }
+
+    /// Needs a Postgres server: set `TEST_DATABASE_URL` to any database on it.
+    /// A scratch database is created next to it and dropped afterwards.
+    #[tokio::test]
+    async fn test_copies_sqlite_to_a_seeded_postgres_and_back() {
+        let Ok(url) = std::env::var("TEST_DATABASE_URL") else {
+            eprintln!("TEST_DATABASE_URL is not set; skipping the Postgres round trip");
+            return;
+        };
+        let (server, _) = url.rsplit_once('/').expect("TEST_DATABASE_URL names a database");
+        let name = format!("maowbot_transfer_{}", Uuid::new_v4().simple());
+        let mut admin = PgConnection::connect(&url).await.unwrap();
+        sqlx::query(&format!("CREATE DATABASE {}", name)).execute(&mut admin).await.unwrap();
+
+        let result = round_trip(&format!("{}/{}", server, name)).await;
+        sqlx::query(&format!("DROP DATABASE {} WITH (FORCE)", name)).execute(&mut admin).await.unwrap();
+        result.unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+//! src/disk.rs
+//!
+//! Free disk space, for the diagnostics check and the low-disk alert. There's
+//! no portable std API for it, so this asks `df` (or PowerShell on Windows).
+
+use std::path::Path;
+
+/// Free bytes on the volume holding `path`.
+#[cfg(windows)]
+pub async fn free_disk_bytes(path: &Path) -> Result<u64, String> {
+    let script = format!("(Get-Item -LiteralPath '{}').PSDrive.Free", path.display());
+    let out = tokio::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .output()
+        .await
+        .map_err(|e| format!("Could not run powershell: {}", e))?;
+    String::from_utf8_lossy(&out.stdout).trim().parse()
+        .map_err(|_| "Could not read the free space".to_string())
+}
+
+/// Free bytes on the volume holding `path`.
+#[cfg(not(windows))]
+pub async fn free_disk_bytes(path: &Path) -> Result<u64, String> {
+    let out = tokio::process::Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| format!("Could not run df: {}", e))?;
+    parse_df_available(&String::from_utf8_lossy(&out.stdout))
+        .ok_or_else(|| "Could not read the free space".to_string())
+}
+
+/// Available bytes from `df -Pk` output (the fourth column of the data line, in KiB).
+#[cfg_attr(windows, allow(dead_code))]
+fn parse_df_available(output: &str) -> Option<u64> {
+    output.lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()
+        .map(|kib| kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_available_space_from_df() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/nvme0n1p2   479152840 201234567 253531234      45% /\n";
+        assert_eq!(parse_df_available(output), Some(253531234 * 1024));
+        assert_eq!(parse_df_available("Filesystem\n"), None);
+    }
+}
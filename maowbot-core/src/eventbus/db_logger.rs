@@ -1,18 +1,156 @@
 // File: maowbot-core/src/eventbus/db_logger.rs
+//
+// Writes chat messages from the event bus to the database in batches. The
+// task that reads the bus never waits on the database: messages collect in a
+// bounded buffer and whole batches go to a separate writer task. When the
+// writer can't keep up (a chat storm, a slow database) the buffer fills; past
+// `shed_percent` of its capacity low-value messages (very short ones and
+// repeats of a text already waiting in the same channel) are dropped, and at
+// capacity every new message is, so publishing on the bus never blocks.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
-use tokio::sync::{mpsc};
-use tokio::time::{sleep, Instant};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{sleep, Instant, MissedTickBehavior};
 use tokio::task::JoinHandle;
-use tracing::{info, error, debug};
+use tracing::{info, error, debug, warn};
 
 use crate::Error;
 use crate::eventbus::{EventBus, BotEvent};
 use crate::repositories::postgres::analytics::{AnalyticsRepo, ChatMessage};
+use crate::services::chat_load;
+
+use super::db_logger_handle::{DbLoggerControl, DbLoggerCommand, DbLoggerStats};
+
+/// Messages at most this long (ignoring whitespace) are the first to be shed.
+const SHORT_MESSAGE_CHARS: usize = 5;
+/// Rows handed to the writer in one batch.
+const MAX_ROWS_PER_WRITE: usize = 5000;
+/// The bus queue is at least this deep, so bursts don't wait on the logger task.
+const MIN_SUBSCRIBER_QUEUE: usize = 1000;
+/// Default buffer capacity.
+pub const DEFAULT_MAX_BUFFERED: usize = 20_000;
+/// Attempts per batch before it is given up.
+const WRITE_ATTEMPTS: u32 = 3;
+
+/// How the logger batches and when it starts shedding.
+#[derive(Debug, Clone)]
+pub struct DbLoggerConfig {
+    /// A batch is written as soon as this many messages are waiting
+    pub batch_size: usize,
+    /// ...or when this much time has passed since the last write
+    pub flush_interval: Duration,
+    /// Messages held while the writer is busy; new ones are dropped beyond this
+    pub max_buffered: usize,
+    /// Low-value messages are shed once the buffer is this full (percent)
+    pub shed_percent: u8,
+}
+
+impl DbLoggerConfig {
+    pub fn new(batch_size: usize, flush_interval_sec: u64) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            flush_interval: Duration::from_secs(flush_interval_sec.max(1)),
+            max_buffered: DEFAULT_MAX_BUFFERED.max(batch_size),
+            shed_percent: 80,
+        }
+    }
+}
+
+/// What happened to a message offered to the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    Accepted,
+    /// Dropped as low-value while the buffer is nearly full
+    Shed,
+    /// Dropped because the buffer is full
+    Dropped,
+}
+
+/// Messages waiting for the writer.
+struct LogBuffer {
+    messages: VecDeque<ChatMessage>,
+    /// How many waiting messages have each (channel, normalized text), for spotting repeats
+    texts: HashMap<(String, String), usize>,
+    max: usize,
+    shed_at: usize,
+}
+
+fn text_key(msg: &ChatMessage) -> (String, String) {
+    let text = msg.message_text.split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (format!("{}/{}", msg.platform, msg.channel), text)
+}
+
+impl LogBuffer {
+    fn new(max: usize, shed_percent: u8) -> Self {
+        let max = max.max(1);
+        Self {
+            messages: VecDeque::new(),
+            texts: HashMap::new(),
+            max,
+            shed_at: (max * usize::from(shed_percent.min(100)) / 100).max(1),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    fn is_shedding(&self) -> bool {
+        self.messages.len() >= self.shed_at
+    }
 
-use super::db_logger_handle::{DbLoggerControl, DbLoggerCommand};
+    fn push(&mut self, msg: ChatMessage) -> Admission {
+        if self.messages.len() >= self.max {
+            return Admission::Dropped;
+        }
+        let key = text_key(&msg);
+        if self.is_shedding() {
+            let short = msg.message_text.chars().filter(|c| !c.is_whitespace()).count() <= SHORT_MESSAGE_CHARS;
+            if short || self.texts.contains_key(&key) {
+                return Admission::Shed;
+            }
+        }
+        *self.texts.entry(key).or_default() += 1;
+        self.messages.push_back(msg);
+        Admission::Accepted
+    }
 
-/// Spawns an asynchronous task to receive events from the bus
-/// and batch-write them to the database. Returns both:
+    /// Takes up to `n` of the oldest messages.
+    fn take(&mut self, n: usize) -> Vec<ChatMessage> {
+        let n = n.min(self.messages.len());
+        let taken: Vec<ChatMessage> = self.messages.drain(..n).collect();
+        for msg in &taken {
+            let key = text_key(msg);
+            if let Some(count) = self.texts.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    self.texts.remove(&key);
+                }
+            }
+        }
+        taken
+    }
+}
+
+/// A batch for the writer; `reply` is answered once it (and every batch
+/// before it) has been written.
+struct WriteJob {
+    messages: Vec<ChatMessage>,
+    reply: Option<oneshot::Sender<Result<(), Error>>>,
+}
+
+/// Spawns the logger with the default buffer capacity and shedding threshold.
+/// Returns both:
 ///   - The `JoinHandle<()>` for the spawned task
 ///   - A `DbLoggerControl` handle, so other code can force flushes at any time.
 pub fn spawn_db_logger_task<T>(
@@ -21,6 +159,20 @@ pub fn spawn_db_logger_task<T>(
     buffer_size: usize,
     flush_interval_sec: u64,
 ) -> (JoinHandle<()>, DbLoggerControl)
+where
+    T: AnalyticsRepo + 'static,
+{
+    spawn_db_logger_task_with_config(event_bus, analytics_repo, DbLoggerConfig::new(buffer_size, flush_interval_sec))
+}
+
+/// Spawns the task that reads chat messages from the bus and the writer task
+/// that inserts them. The returned handle finishes once both have stopped,
+/// after a final flush on shutdown.
+pub fn spawn_db_logger_task_with_config<T>(
+    event_bus: &EventBus,
+    analytics_repo: T,
+    config: DbLoggerConfig,
+) -> (JoinHandle<()>, DbLoggerControl)
 where
     T: AnalyticsRepo + 'static,
 {
@@ -29,20 +181,29 @@ where
 
     // We'll create a control channel for flush commands
     let (cmd_tx, mut cmd_rx) = mpsc::channel::<DbLoggerCommand>(8);
+    let stats = Arc::new(DbLoggerStats::default());
 
     // The control handle we can give to others
-    let control_handle = DbLoggerControl::new(cmd_tx);
+    let control_handle = DbLoggerControl::new(cmd_tx, stats.clone());
 
     let join_handle = tokio::spawn(async move {
-        let mut rx = event_bus_cloned.subscribe(Some(buffer_size)).await;
+        let mut rx = event_bus_cloned
+            .subscribe(Some(config.batch_size.max(MIN_SUBSCRIBER_QUEUE)))
+            .await;
+
+        // One batch being written and one waiting; everything else stays in the buffer
+        let (job_tx, job_rx) = mpsc::channel::<WriteJob>(1);
+        let writer = tokio::spawn(run_writer(analytics_repo, job_rx, stats.clone()));
 
-        let mut buffer = Vec::with_capacity(buffer_size);
-        let flush_interval = Duration::from_secs(flush_interval_sec);
+        let mut buffer = LogBuffer::new(config.max_buffered, config.shed_percent);
         let mut last_flush = Instant::now();
+        let mut ticker = tokio::time::interval(config.flush_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut shed_since_overload: u64 = 0;
 
         info!(
-            "DB logger task started with batch_size={} flush_interval={}s",
-            buffer_size, flush_interval_sec
+            "DB logger task started with batch_size={} flush_interval={}s max_buffered={}",
+            config.batch_size, config.flush_interval.as_secs(), config.max_buffered
         );
 
         // MAIN LOOP
@@ -50,19 +211,40 @@ where
             tokio::select! {
                 biased;
 
-                // 1) Chat messages from the event bus
+                // 1) External control commands
+                Some(cmd) = cmd_rx.recv() => {
+                    match cmd {
+                        // synchronous forced flush request
+                        DbLoggerCommand::FlushNow(reply_tx) => {
+                            debug!("db_logger: got FlushNow command");
+                            flush_all(&mut buffer, &job_tx, reply_tx).await;
+                            stats.buffered.store(0, Ordering::Relaxed);
+                            last_flush = Instant::now();
+                        }
+                    }
+                },
+
+                // 2) Chat messages from the event bus
                 maybe_event = rx.recv() => {
                     match maybe_event {
                         Some(event) => {
-                            if let Some(cm) = convert_to_chat_message(&event) {
-                                buffer.push(cm);
-                            }
-                            if buffer.len() >= buffer_size {
-                                if let Err(e) = insert_batch(&analytics_repo, &mut buffer).await {
-                                    error!("Error inserting batch: {:?}", e);
+                            let Some(cm) = convert_to_chat_message(&event) else { continue };
+                            let counter = match buffer.push(cm) {
+                                Admission::Accepted => None,
+                                Admission::Shed => Some(&stats.shed),
+                                Admission::Dropped => Some(&stats.dropped),
+                            };
+                            if let Some(counter) = counter {
+                                if shed_since_overload == 0 {
+                                    warn!("DB logger buffer at {} messages => shedding low-value chat messages", buffer.len());
                                 }
+                                shed_since_overload += 1;
+                                counter.fetch_add(1, Ordering::Relaxed);
+                            }
+                            if buffer.len() >= config.batch_size && dispatch(&mut buffer, &job_tx) {
                                 last_flush = Instant::now();
                             }
+                            stats.buffered.store(buffer.len(), Ordering::Relaxed);
                         },
                         None => {
                             info!("DB logger channel closed => break from loop.");
@@ -71,25 +253,6 @@ where
                     }
                 },
 
-                // 2) External control commands
-                Some(cmd) = cmd_rx.recv() => {
-                    match cmd {
-                        // synchronous forced flush request
-                        DbLoggerCommand::FlushNow(reply_tx) => {
-                            debug!("db_logger: got FlushNow command");
-                            let res = if !buffer.is_empty() {
-                                insert_batch(&analytics_repo, &mut buffer).await
-                            } else {
-                                Ok(())
-                            };
-                            if let Err(e) = reply_tx.send(res) {
-                                error!("db_logger: flush_now => oneshot send error: {:?}", e);
-                            }
-                            last_flush = Instant::now();
-                        }
-                    }
-                },
-
                 // 3) Shutdown
                 Ok(_) = shutdown_rx.changed() => {
                     if *shutdown_rx.borrow() {
@@ -99,30 +262,44 @@ where
                 },
 
                 // 4) Periodic flush
-                _ = sleep(flush_interval) => {
-                    if !buffer.is_empty() && last_flush.elapsed() >= flush_interval {
-                        if let Err(e) = insert_batch(&analytics_repo, &mut buffer).await {
-                            error!("Periodic flush error: {:?}", e);
-                        }
+                _ = ticker.tick() => {
+                    if !buffer.is_empty() && last_flush.elapsed() >= config.flush_interval && dispatch(&mut buffer, &job_tx) {
                         last_flush = Instant::now();
                     }
+                    stats.buffered.store(buffer.len(), Ordering::Relaxed);
                 }
             }
+
+            if shed_since_overload > 0 && !buffer.is_shedding() {
+                info!("DB logger caught up; {} chat messages were not logged", shed_since_overload);
+                shed_since_overload = 0;
+            }
         }
 
         info!("DB logger: draining any remaining messages after loop exit.");
         while let Ok(event) = rx.try_recv() {
             if let Some(cm) = convert_to_chat_message(&event) {
-                buffer.push(cm);
+                if buffer.push(cm) != Admission::Accepted {
+                    stats.dropped.fetch_add(1, Ordering::Relaxed);
+                }
             }
         }
 
         if !buffer.is_empty() {
             info!("DB logger final flush: {} messages remain.", buffer.len());
-            if let Err(e) = insert_batch(&analytics_repo, &mut buffer).await {
-                error!("Final flush error: {:?}", e);
+        }
+        while !buffer.is_empty() {
+            let messages = buffer.take(MAX_ROWS_PER_WRITE);
+            if job_tx.send(WriteJob { messages, reply: None }).await.is_err() {
+                error!("DB logger writer stopped before the final flush");
+                break;
             }
         }
+        stats.buffered.store(0, Ordering::Relaxed);
+        drop(job_tx);
+        if let Err(e) = writer.await {
+            error!("DB logger writer task failed: {:?}", e);
+        }
 
         info!("DB logger task exited completely.");
     });
@@ -130,8 +307,96 @@ where
     (join_handle, control_handle)
 }
 
+/// Hands the oldest messages to the writer if it has room; returns false
+/// (leaving them buffered) while it is still busy.
+fn dispatch(buffer: &mut LogBuffer, job_tx: &mpsc::Sender<WriteJob>) -> bool {
+    match job_tx.try_reserve() {
+        Ok(permit) => {
+            permit.send(WriteJob { messages: buffer.take(MAX_ROWS_PER_WRITE), reply: None });
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Hands the whole buffer to the writer in batches of at most
+/// `MAX_ROWS_PER_WRITE`, with `reply` on the last one (an empty batch when
+/// nothing is buffered, so the reply still waits for earlier batches).
+async fn flush_all(
+    buffer: &mut LogBuffer,
+    job_tx: &mpsc::Sender<WriteJob>,
+    reply: oneshot::Sender<Result<(), Error>>,
+) {
+    let mut reply = Some(reply);
+    loop {
+        let messages = buffer.take(MAX_ROWS_PER_WRITE);
+        let last = buffer.is_empty();
+        let job = WriteJob { messages, reply: if last { reply.take() } else { None } };
+        if let Err(mpsc::error::SendError(job)) = job_tx.send(job).await {
+            if let Some(reply) = job.reply.or(reply) {
+                let _ = reply.send(Err(Error::EventBus("db_logger writer is not running".into())));
+            }
+            return;
+        }
+        if last {
+            return;
+        }
+    }
+}
+
+/// Writes batches in order. A reply reports the first failure among the
+/// batches written since the previous reply.
+async fn run_writer<T: AnalyticsRepo>(
+    repo: T,
+    mut jobs: mpsc::Receiver<WriteJob>,
+    stats: Arc<DbLoggerStats>,
+) {
+    let mut failure = None;
+    while let Some(job) = jobs.recv().await {
+        match write_batch(&repo, &job.messages).await {
+            Ok(()) => {
+                stats.written.fetch_add(job.messages.len() as u64, Ordering::Relaxed);
+            }
+            Err(e) => {
+                error!("DB logger gave up on {} chat messages: {:?}", job.messages.len(), e);
+                stats.failed.fetch_add(job.messages.len() as u64, Ordering::Relaxed);
+                failure.get_or_insert(e);
+            }
+        }
+        if let Some(reply) = job.reply {
+            let result = failure.take().map_or(Ok(()), Err);
+            if reply.send(result).is_err() {
+                debug!("db_logger: flush_now caller went away");
+            }
+        }
+    }
+}
+
+/// Bulk-inserts one batch, retrying a couple of times with a short backoff.
+async fn write_batch<T: AnalyticsRepo>(repo: &T, messages: &[ChatMessage]) -> Result<(), Error> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+    let mut attempt = 1;
+    loop {
+        match repo.insert_chat_messages(messages).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < WRITE_ATTEMPTS => {
+                warn!("DB logger write failed (attempt {}): {:?}", attempt, e);
+                sleep(Duration::from_millis(500 * u64::from(attempt))).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 fn convert_to_chat_message(event: &BotEvent) -> Option<ChatMessage> {
-    if let BotEvent::ChatMessage { platform, channel, user, text, timestamp, metadata: _ } = event {
+    if let BotEvent::ChatMessage { platform, channel, user, text, timestamp, metadata } = event {
+        // Load test chat isn't worth keeping
+        if chat_load::is_generated(metadata) {
+            return None;
+        }
         Some(ChatMessage {
             message_id: uuid::Uuid::new_v4(),
             platform: platform.clone(),
@@ -146,16 +411,204 @@ fn convert_to_chat_message(event: &BotEvent) -> Option<ChatMessage> {
     }
 }
 
-/// Bulk-insert the entire buffer at once.
-async fn insert_batch<T: AnalyticsRepo>(
-    repo: &T,
-    buffer: &mut Vec<ChatMessage>,
-) -> Result<(), Error> {
-    if buffer.is_empty() {
-        return Ok(());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, AtomicUsize};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+    use maowbot_common::models::analytics::{BotEvent as AnalyticsEvent, ChatSession};
+
+    /// Records each inserted batch; the first `failures` inserts fail.
+    #[derive(Clone, Default)]
+    struct MockAnalytics {
+        batches: Arc<parking_lot::Mutex<Vec<Vec<ChatMessage>>>>,
+        failures: Arc<AtomicU32>,
+        attempts: Arc<AtomicUsize>,
+        delay: Duration,
+    }
+
+    impl MockAnalytics {
+        fn failing(failures: u32) -> Self {
+            Self { failures: Arc::new(AtomicU32::new(failures)), ..Default::default() }
+        }
+
+        fn rows(&self) -> usize {
+            self.batches.lock().iter().map(Vec::len).sum()
+        }
+    }
+
+    #[async_trait]
+    impl AnalyticsRepo for MockAnalytics {
+        async fn insert_chat_message(&self, msg: &ChatMessage) -> Result<(), Error> {
+            self.insert_chat_messages(std::slice::from_ref(msg)).await
+        }
+        async fn insert_chat_messages(&self, msgs: &[ChatMessage]) -> Result<(), Error> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if !self.delay.is_zero() {
+                sleep(self.delay).await;
+            }
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(Error::Database(sqlx::Error::PoolTimedOut));
+            }
+            self.batches.lock().push(msgs.to_vec());
+            Ok(())
+        }
+        async fn get_recent_messages(&self, _: &str, _: &str, _: i64) -> Result<Vec<ChatMessage>, Error> {
+            Ok(Vec::new())
+        }
+        async fn insert_chat_session(&self, _: &ChatSession) -> Result<(), Error> {
+            Ok(())
+        }
+        async fn close_chat_session(&self, _: Uuid, _: DateTime<Utc>, _: i64) -> Result<(), Error> {
+            Ok(())
+        }
+        async fn insert_bot_event(&self, _: &AnalyticsEvent) -> Result<(), Error> {
+            Ok(())
+        }
+        async fn update_daily_stats(&self, _: &str, _: i64, _: i64) -> Result<(), Error> {
+            Ok(())
+        }
+        async fn get_messages_for_user(
+            &self, _: Uuid, _: i64, _: i64, _: Option<&str>, _: Option<&str>, _: Option<&str>,
+        ) -> Result<Vec<ChatMessage>, Error> {
+            Ok(Vec::new())
+        }
+        async fn reassign_user_messages(&self, _: Uuid, _: Uuid) -> Result<u64, Error> {
+            Ok(0)
+        }
+    }
+
+    fn msg(channel: &str, text: &str) -> ChatMessage {
+        ChatMessage {
+            message_id: uuid::Uuid::new_v4(),
+            platform: "twitch-irc".into(),
+            channel: channel.into(),
+            user_id: uuid::Uuid::nil(),
+            message_text: text.into(),
+            timestamp: Utc::now(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_sheds_low_value_messages_before_dropping() {
+        let mut buffer = LogBuffer::new(4, 50);
+        assert_eq!(buffer.push(msg("#a", "lol")), Admission::Accepted);
+        assert_eq!(buffer.push(msg("#a", "copy this pasta")), Admission::Accepted);
+
+        // Half full: short messages and repeats in the same channel are shed
+        assert_eq!(buffer.push(msg("#a", "W")), Admission::Shed);
+        assert_eq!(buffer.push(msg("#a", "COPY  this pasta")), Admission::Shed);
+        assert_eq!(buffer.push(msg("#b", "copy this pasta")), Admission::Accepted);
+        assert_eq!(buffer.push(msg("#a", "a real question?")), Admission::Accepted);
+        assert_eq!(buffer.push(msg("#a", "another real one")), Admission::Dropped);
+
+        assert_eq!(buffer.take(3).len(), 3);
+        assert!(!buffer.is_shedding());
+        assert_eq!(buffer.push(msg("#a", "copy this pasta")), Admission::Accepted);
+
+        // Taking messages forgets only their texts; the one still waiting is a repeat
+        assert_eq!(buffer.push(msg("#a", "A real  question?")), Admission::Shed);
+        assert_eq!(buffer.texts.len(), 2);
+    }
+
+    fn job(n: usize, reply: Option<oneshot::Sender<Result<(), Error>>>) -> WriteJob {
+        WriteJob { messages: (0..n).map(|i| msg("#a", &format!("message number {i}"))).collect(), reply }
+    }
+
+    #[tokio::test]
+    async fn test_flush_now_splits_the_buffer_and_replies_with_the_last_batch() {
+        let mut buffer = LogBuffer::new(20_000, 100);
+        for i in 0..(2 * MAX_ROWS_PER_WRITE + 1) {
+            buffer.push(msg("#a", &format!("message number {i}")));
+        }
+        let (job_tx, mut job_rx) = mpsc::channel(8);
+        let (reply_tx, _reply_rx) = oneshot::channel();
+        flush_all(&mut buffer, &job_tx, reply_tx).await;
+        drop(job_tx);
+
+        let mut jobs = Vec::new();
+        while let Some(job) = job_rx.recv().await {
+            jobs.push((job.messages.len(), job.reply.is_some()));
+        }
+        assert_eq!(jobs, vec![(MAX_ROWS_PER_WRITE, false), (MAX_ROWS_PER_WRITE, false), (1, true)]);
+        assert!(buffer.is_empty());
+
+        // Nothing buffered: an empty batch still carries the reply
+        let (job_tx, mut job_rx) = mpsc::channel(8);
+        let (reply_tx, _reply_rx) = oneshot::channel();
+        flush_all(&mut buffer, &job_tx, reply_tx).await;
+        let job = job_rx.recv().await.unwrap();
+        assert!(job.messages.is_empty() && job.reply.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_flush_reply_waits_for_earlier_batches() {
+        let repo = MockAnalytics { delay: Duration::from_millis(20), ..Default::default() };
+        let stats = Arc::new(DbLoggerStats::default());
+        let (job_tx, job_rx) = mpsc::channel(4);
+        let writer = tokio::spawn(run_writer(repo.clone(), job_rx, stats.clone()));
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        job_tx.send(job(3, None)).await.unwrap();
+        job_tx.send(job(2, Some(reply_tx))).await.unwrap();
+        reply_rx.await.unwrap().unwrap();
+
+        let sizes: Vec<usize> = repo.batches.lock().iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![3, 2]);
+        assert_eq!(stats.written.load(Ordering::Relaxed), 5);
+        drop(job_tx);
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_batch_retries_before_giving_up() {
+        let repo = MockAnalytics::failing(WRITE_ATTEMPTS - 1);
+        write_batch(&repo, &job(4, None).messages).await.unwrap();
+        assert_eq!(repo.attempts.load(Ordering::SeqCst), WRITE_ATTEMPTS as usize);
+        assert_eq!(repo.rows(), 4);
+
+        // Every attempt fails: the batch is counted as failed and the next reply says so
+        let repo = MockAnalytics::failing(u32::MAX);
+        let stats = Arc::new(DbLoggerStats::default());
+        let (job_tx, job_rx) = mpsc::channel(4);
+        let writer = tokio::spawn(run_writer(repo.clone(), job_rx, stats.clone()));
+        let (reply_tx, reply_rx) = oneshot::channel();
+        job_tx.send(job(4, None)).await.unwrap();
+        job_tx.send(job(0, Some(reply_tx))).await.unwrap();
+        assert!(reply_rx.await.unwrap().is_err());
+        assert_eq!(repo.attempts.load(Ordering::SeqCst), WRITE_ATTEMPTS as usize);
+        assert_eq!(stats.failed.load(Ordering::Relaxed), 4);
+        assert_eq!(stats.written.load(Ordering::Relaxed), 0);
+
+        // The failure was reported once; a later flush that writes fine succeeds
+        repo.failures.store(0, Ordering::SeqCst);
+        let (reply_tx, reply_rx) = oneshot::channel();
+        job_tx.send(job(1, Some(reply_tx))).await.unwrap();
+        reply_rx.await.unwrap().unwrap();
+        drop(job_tx);
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_writes_everything_still_buffered() {
+        let bus = EventBus::new();
+        let repo = MockAnalytics::default();
+        // A batch size and interval that never trigger on their own
+        let (handle, control) = spawn_db_logger_task_with_config(&bus, repo.clone(), DbLoggerConfig::new(1_000, 3_600));
+        // Answered from inside the task, so the logger has subscribed by now
+        control.flush_now().await.unwrap();
+        for i in 0..7 {
+            bus.publish_chat("twitch-irc", "#a", &Uuid::new_v4().to_string(), &format!("hello number {i}")).await;
+        }
+        bus.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), handle).await.unwrap().unwrap();
+
+        assert_eq!(repo.rows(), 7);
+        assert_eq!(control.stats().written, 7);
+        assert_eq!(control.stats().buffered, 0);
     }
-    // Use the new bulk method
-    repo.insert_chat_messages(buffer).await?;
-    buffer.clear();
-    Ok(())
 }
@@ -1,6 +1,9 @@
 // File: maowbot-core/src/eventbus/db_logger_handle.rs
 //
-// A small control handle for forcing flushes in the db_logger task.
+// A small control handle for forcing flushes in the db_logger task and
+// reading its counters.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use tokio::sync::{mpsc, oneshot};
 use tracing::trace;
 use crate::Error;
@@ -15,19 +18,56 @@ pub enum DbLoggerCommand {
     // Optionally, you can add other commands later if needed...
 }
 
+/// Counters kept by the db_logger tasks since startup.
+#[derive(Debug, Default)]
+pub struct DbLoggerStats {
+    /// Messages waiting for the writer
+    pub buffered: AtomicUsize,
+    pub written: AtomicU64,
+    /// Low-value messages dropped while the buffer was nearly full
+    pub shed: AtomicU64,
+    /// Messages dropped because the buffer was full
+    pub dropped: AtomicU64,
+    /// Messages in batches that still failed after retrying
+    pub failed: AtomicU64,
+}
+
+/// A point-in-time copy of `DbLoggerStats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DbLoggerStatsSnapshot {
+    pub buffered: usize,
+    pub written: u64,
+    pub shed: u64,
+    pub dropped: u64,
+    pub failed: u64,
+}
+
 /// A handle that we can clone and keep in the user API or plugin manager,
 /// allowing them to call `.flush_now()` at any time.
 #[derive(Clone)]
 pub struct DbLoggerControl {
     cmd_tx: mpsc::Sender<DbLoggerCommand>,
+    stats: Arc<DbLoggerStats>,
 }
 
 impl DbLoggerControl {
-    pub fn new(cmd_tx: mpsc::Sender<DbLoggerCommand>) -> Self {
-        Self { cmd_tx }
+    pub fn new(cmd_tx: mpsc::Sender<DbLoggerCommand>, stats: Arc<DbLoggerStats>) -> Self {
+        Self { cmd_tx, stats }
+    }
+
+    /// What the logger has written and dropped so far.
+    pub fn stats(&self) -> DbLoggerStatsSnapshot {
+        DbLoggerStatsSnapshot {
+            buffered: self.stats.buffered.load(Ordering::Relaxed),
+            written: self.stats.written.load(Ordering::Relaxed),
+            shed: self.stats.shed.load(Ordering::Relaxed),
+            dropped: self.stats.dropped.load(Ordering::Relaxed),
+            failed: self.stats.failed.load(Ordering::Relaxed),
+        }
     }
 
     /// Request a forced flush of all queued chat messages in the db_logger.
+    /// Returns once they and every batch before them have been written.
     /// This returns an error if the db_logger task is gone or if the flush
     /// fails internally.
     pub async fn flush_now(&self) -> Result<(), Error> {
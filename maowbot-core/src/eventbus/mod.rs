@@ -6,14 +6,21 @@
 pub mod db_logger;
 pub mod db_logger_handle;
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 use tokio::sync::{mpsc, watch, Mutex};
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Deserialize};
 use maowbot_common::models::platform::Platform;
+use maowbot_common::models::alert_rule::{AlertKind, AlertSeverity};
+use maowbot_common::models::redeem_approval::ApprovalStatus;
+use crate::platforms::twitch::requests::chatters::Chatter;
 
 /// Global event type that various parts of the bot can publish or subscribe to.
 /// Extend this enum with whatever events your system needs.
+// Variants are matched on by value all over the bot, so the big payloads stay unboxed
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum BotEvent {
     /// Represents a chat message, possibly from Twitch, Discord, or VRChat.
@@ -35,11 +42,283 @@ pub enum BotEvent {
     /// NEW: We add a variant for Twitch EventSub notifications.
     /// This wraps a typed event from the newly introduced TwitchEventSubData enum.
     TwitchEventSub(TwitchEventSubData),
+
+    /// Follow and subscription events from a Kick channel's websocket.
+    Kick(KickEventData),
+
+    /// A tip received through Streamlabs or StreamElements, already stored
+    /// and normalized by the DonationService.
+    Donation(maowbot_common::models::donation::Donation),
+
+    /// A viewer reached a sub anniversary, sub streak or follow anniversary,
+    /// published once per milestone by the MilestoneService.
+    ViewerMilestone(maowbot_common::models::milestone::ViewerMilestone),
+
+    /// The program scene of a connected OBS instance changed (polled by ObsRuntime).
+    ObsSceneChanged {
+        instance: u32,
+        scene: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Live heart rate from Pulsoid or HypeRate, published by the
+    /// HeartRateService whenever the BPM changes.
+    HeartRate {
+        source: String,
+        bpm: u16,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// An OAuth credential could not be renewed before it expires. Published by
+    /// the credential refresh scheduler so notifiers can alert before the
+    /// platform connection drops.
+    CredentialRefreshFailed {
+        credential_id: uuid::Uuid,
+        platform: String,
+        user_name: String,
+        expires_at: Option<DateTime<Utc>>,
+        error: String,
+    },
+
+    /// A supervised platform runtime changed connection state (connected,
+    /// degraded, reconnecting, failed or stopped). Published by the
+    /// PlatformManager's connection supervisor.
+    PlatformConnectionChanged {
+        platform: String,
+        account_name: String,
+        state: String,
+        attempt: u32,
+        error: Option<String>,
+        next_retry_at: Option<DateTime<Utc>>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A bot_config setting changed (set, updated or deleted). Published by the
+    /// SettingsRegistry when it reloads; secret values are redacted.
+    ConfigChanged {
+        key: String,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A VRChat friend joined or left the streamer's instance. Published by the
+    /// VRChatPresenceService, which polls the friends list; `watched` is set
+    /// for friends on the `vrchat.presence.watch_friends` list.
+    VRChatPresence {
+        joined: bool,
+        user_id: String,
+        display_name: String,
+        location: String,
+        watched: bool,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Viewers joined or left the broadcaster's Twitch chat. Published by the
+    /// ChatterPresenceService, which polls Helix Get Chatters, once per poll
+    /// with everyone who changed since the last one.
+    TwitchChatters {
+        /// Lowercase channel login, without '#'
+        channel: String,
+        joined: Vec<Chatter>,
+        left: Vec<Chatter>,
+        /// Viewers in chat after the change
+        present: usize,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A segment of the broadcaster's Twitch schedule starts within
+    /// `twitch.schedule.starting_soon_minutes` and the stream isn't live yet.
+    /// Published by the ScheduleService once per occurrence.
+    TwitchScheduleStartingSoon {
+        /// Lowercase channel login, without '#'
+        channel: String,
+        segment_id: String,
+        title: String,
+        /// Empty when the segment has no category
+        category: String,
+        start_time: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Chat, emotes, bits or subs spiked in the broadcaster's Twitch channel.
+    /// Published by the HypeDetector at most once per `hype.cooldown_seconds`,
+    /// for markers, replay clips and stream recaps.
+    HypeMoment {
+        /// Lowercase channel login, without '#'
+        channel: String,
+        /// The signal furthest over its threshold
+        kind: HypeKind,
+        /// How far over their thresholds the signals were; 1.0 = just at one
+        score: f64,
+        /// Counts over the detection window
+        messages: u32,
+        emote_messages: u32,
+        bits: u64,
+        subs: u64,
+        /// Start of the detection window
+        started_at: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A chat responder matched a message and passed its chance and
+    /// cooldowns. Published by the ResponderService after it answers, so
+    /// pipelines can run further actions.
+    ResponderTriggered {
+        platform: String,
+        channel: String,
+        /// The chatter's display name
+        user: String,
+        platform_user_id: String,
+        responder: String,
+        /// The part of the message that matched
+        matched: String,
+        text: String,
+        /// What was sent back; None when the responder only publishes
+        response: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A redemption of a redeem that needs approval was queued, approved,
+    /// denied or expired. Published by the RedeemApprovalService.
+    RedeemApproval {
+        approval_id: uuid::Uuid,
+        status: ApprovalStatus,
+        reward_name: String,
+        channel: String,
+        /// The redeemer's display name
+        user: String,
+        user_input: String,
+        /// Who approved or denied it
+        decided_by: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A moderation rule with a category timed out or banned a chatter on the
+    /// escalation ladder. Published by the ModerationService.
+    ModerationEscalated {
+        user_id: uuid::Uuid,
+        /// The chatter's Twitch login
+        user: String,
+        channel: String,
+        category: String,
+        rule: String,
+        /// Counts from 1 within the category
+        offense: i64,
+        /// The ladder step, e.g. "timeout:600" or "ban"
+        action: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// An operational problem was raised (a disconnect, a credential that
+    /// can't be renewed, failing pipelines, low disk). Published by the
+    /// AlertingService after the alert rules ran.
+    OpsAlert {
+        kind: AlertKind,
+        severity: AlertSeverity,
+        /// What the alert is about, e.g. "twitch-irc:mybot"
+        key: String,
+        title: String,
+        message: String,
+        /// Whether a desktop rule let it through, for the GUI to show
+        desktop: bool,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Something happened in the VRChat group set as `vrchat.group.id`.
+    /// Published by the VRChatGroupService: join requests are found by polling,
+    /// approvals, rejections and posts are the ones made through the bot.
+    VRChatGroup {
+        kind: VRChatGroupEventKind,
+        group_id: String,
+        /// The requester, for join-request events
+        user_id: Option<String>,
+        display_name: Option<String>,
+        /// The announcement title, for posts
+        title: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A chat vote among OSC effects closed with a winner. Published by the
+    /// CrowdVoteService after it toggled the winner's chat control, so
+    /// pipelines can run the effect's other actions.
+    CrowdVoteDecided {
+        /// Lowercase channel login, without '#'
+        channel: String,
+        title: String,
+        /// The winning option's label
+        winner: String,
+        /// The OSC chat control it toggled, if the option has one
+        control: Option<String>,
+        /// Sum of the winner's vote weights
+        weight: u64,
+        /// How many viewers voted at all
+        voters: u32,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// What set off a `BotEvent::HypeMoment`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HypeKind {
+    ChatSurge,
+    EmoteBurst,
+    Bits,
+    Subs,
+}
+
+impl HypeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HypeKind::ChatSurge => "chat_surge",
+            HypeKind::EmoteBurst => "emote_burst",
+            HypeKind::Bits => "bits",
+            HypeKind::Subs => "subs",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [HypeKind::ChatSurge, HypeKind::EmoteBurst, HypeKind::Bits, HypeKind::Subs]
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+    }
+}
+
+/// What a `BotEvent::VRChatGroup` is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VRChatGroupEventKind {
+    JoinRequest,
+    RequestApproved,
+    RequestRejected,
+    Announcement,
+}
+
+impl VRChatGroupEventKind {
+    pub fn event_type(self) -> &'static str {
+        match self {
+            VRChatGroupEventKind::JoinRequest => "vrchat.group_join_request",
+            VRChatGroupEventKind::RequestApproved => "vrchat.group_request_approved",
+            VRChatGroupEventKind::RequestRejected => "vrchat.group_request_rejected",
+            VRChatGroupEventKind::Announcement => "vrchat.group_post",
+        }
+    }
+
+    pub fn from_event_type(event_type: &str) -> Option<Self> {
+        [
+            VRChatGroupEventKind::JoinRequest,
+            VRChatGroupEventKind::RequestApproved,
+            VRChatGroupEventKind::RequestRejected,
+            VRChatGroupEventKind::Announcement,
+        ]
+        .into_iter()
+        .find(|kind| kind.event_type() == event_type)
+    }
 }
 
 /// This is the new type used by BotEvent::TwitchEventSub. Each variant corresponds to one of
 /// the supported Twitch EventSub event types. For the actual fields, see the `events.rs` file in
 /// the `twitch_eventsub` module.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum TwitchEventSubData {
     StreamOnline(crate::platforms::twitch_eventsub::events::StreamOnline),
@@ -85,6 +364,26 @@ pub enum TwitchEventSubData {
     ChannelPointsCustomRewardRedemptionUpdate(
         crate::platforms::twitch_eventsub::events::ChannelPointsCustomRewardRedemption
     ),
+    UserWhisperMessage(crate::platforms::twitch_eventsub::events::UserWhisperMessage),
+}
+
+/// Payload of BotEvent::Kick; see `platforms/kick/events.rs`.
+#[derive(Debug, Clone)]
+pub enum KickEventData {
+    Follow(crate::platforms::kick::events::KickFollow),
+    Subscription(crate::platforms::kick::events::KickSubscription),
+    GiftedSubscriptions(crate::platforms::kick::events::KickGiftedSubscriptions),
+}
+
+impl KickEventData {
+    /// The follower, subscriber or gifter.
+    pub fn username(&self) -> &str {
+        match self {
+            KickEventData::Follow(f) => f.username.as_deref().unwrap_or_default(),
+            KickEventData::Subscription(s) => &s.username,
+            KickEventData::GiftedSubscriptions(g) => &g.gifter_username,
+        }
+    }
 }
 
 impl BotEvent {
@@ -94,6 +393,29 @@ impl BotEvent {
             BotEvent::ChatMessage { .. } => "chat_message".to_string(),
             BotEvent::Tick => "tick".to_string(),
             BotEvent::SystemMessage(_) => "system_message".to_string(),
+            BotEvent::CredentialRefreshFailed { .. } => "credential.refresh_failed".to_string(),
+            BotEvent::PlatformConnectionChanged { .. } => "platform.connection_changed".to_string(),
+            BotEvent::ConfigChanged { .. } => "config.changed".to_string(),
+            BotEvent::Donation(_) => "donation".to_string(),
+            BotEvent::ViewerMilestone(_) => "twitch.milestone".to_string(),
+            BotEvent::HeartRate { .. } => "heart_rate".to_string(),
+            BotEvent::ObsSceneChanged { .. } => "obs.scene_changed".to_string(),
+            BotEvent::VRChatPresence { joined: true, .. } => "vrchat.friend_joined".to_string(),
+            BotEvent::VRChatPresence { joined: false, .. } => "vrchat.friend_left".to_string(),
+            BotEvent::VRChatGroup { kind, .. } => kind.event_type().to_string(),
+            BotEvent::TwitchChatters { .. } => "twitch.chatters_changed".to_string(),
+            BotEvent::TwitchScheduleStartingSoon { .. } => "twitch.schedule_starting_soon".to_string(),
+            BotEvent::HypeMoment { .. } => "twitch.hype_moment".to_string(),
+            BotEvent::ResponderTriggered { .. } => "responder.triggered".to_string(),
+            BotEvent::RedeemApproval { .. } => "redeem.approval".to_string(),
+            BotEvent::ModerationEscalated { .. } => "moderation.escalated".to_string(),
+            BotEvent::OpsAlert { .. } => "ops.alert".to_string(),
+            BotEvent::CrowdVoteDecided { .. } => "crowd.vote_decided".to_string(),
+            BotEvent::Kick(data) => match data {
+                KickEventData::Follow(_) => "kick.follow".to_string(),
+                KickEventData::Subscription(_) => "kick.subscription".to_string(),
+                KickEventData::GiftedSubscriptions(_) => "kick.subscription.gift".to_string(),
+            },
             BotEvent::TwitchEventSub(data) => match data {
                 TwitchEventSubData::StreamOnline(_) => "stream.online".to_string(),
                 TwitchEventSubData::StreamOffline(_) => "stream.offline".to_string(),
@@ -126,15 +448,245 @@ impl BotEvent {
                 TwitchEventSubData::ChannelPointsCustomRewardRemove(_) => "channel.channel_points_custom_reward.remove".to_string(),
                 TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(_) => "channel.channel_points_custom_reward_redemption.add".to_string(),
                 TwitchEventSubData::ChannelPointsCustomRewardRedemptionUpdate(_) => "channel.channel_points_custom_reward_redemption.update".to_string(),
+                TwitchEventSubData::UserWhisperMessage(_) => "user.whisper.message".to_string(),
             }
         }
     }
     
+    /// Build a synthetic event from an event type string and a JSON payload.
+    /// Used by pipeline test-fire and simulation tooling. Returns None if the
+    /// event type is unknown or the payload does not match its schema.
+    pub fn from_synthetic(event_type: &str, data: &serde_json::Value) -> Option<BotEvent> {
+        let str_field = |key: &str, default: &str| {
+            data.get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or(default)
+                .to_string()
+        };
+
+        match event_type {
+            "chat_message" => Some(BotEvent::ChatMessage {
+                platform: str_field("platform", "twitch-irc"),
+                channel: str_field("channel", "#test"),
+                user: str_field("user", "test_user"),
+                text: str_field("text", "test message"),
+                timestamp: Utc::now(),
+                metadata: data.get("metadata")
+                    .and_then(|v| v.as_object())
+                    .cloned()
+                    .unwrap_or_default(),
+            }),
+            "tick" => Some(BotEvent::Tick),
+            "system_message" => Some(BotEvent::SystemMessage(str_field("text", "test"))),
+            "credential.refresh_failed" => Some(BotEvent::CredentialRefreshFailed {
+                credential_id: data.get("credential_id")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| uuid::Uuid::parse_str(s).ok())
+                    .unwrap_or_else(uuid::Uuid::nil),
+                platform: str_field("platform", "twitch"),
+                user_name: str_field("user_name", "test_user"),
+                expires_at: Some(Utc::now()),
+                error: str_field("error", "simulated refresh failure"),
+            }),
+            "platform.connection_changed" => Some(BotEvent::PlatformConnectionChanged {
+                platform: str_field("platform", "twitch-irc"),
+                account_name: str_field("account_name", "test_user"),
+                state: str_field("state", "reconnecting"),
+                attempt: data.get("attempt").and_then(|v| v.as_u64()).unwrap_or(1) as u32,
+                error: data.get("error").and_then(|v| v.as_str()).map(String::from),
+                next_retry_at: None,
+                timestamp: Utc::now(),
+            }),
+            "config.changed" => Some(BotEvent::ConfigChanged {
+                key: str_field("key", "test_key"),
+                old_value: data.get("old_value").and_then(|v| v.as_str()).map(String::from),
+                new_value: data.get("new_value").and_then(|v| v.as_str()).map(String::from),
+                timestamp: Utc::now(),
+            }),
+            "donation" => {
+                let source = str_field("source", "streamlabs").parse().ok()?;
+                let amount = data.get("amount")
+                    .and_then(maowbot_common::models::donation::parse_amount)
+                    .unwrap_or(5.0);
+                let currency = str_field("currency", "USD");
+                Some(BotEvent::Donation(
+                    maowbot_common::models::donation::Donation::new(
+                        source,
+                        &str_field("donor_name", "test_user"),
+                        amount,
+                        Some(&currency),
+                    )
+                    .with_message(data.get("message").and_then(|v| v.as_str()).map(String::from))
+                    .normalize(&currency, &HashMap::new()),
+                ))
+            }
+            "twitch.milestone" => Some(BotEvent::ViewerMilestone(
+                maowbot_common::models::milestone::ViewerMilestone {
+                    milestone_id: uuid::Uuid::new_v4(),
+                    channel: str_field("channel", "test_channel"),
+                    twitch_user_id: str_field("twitch_user_id", "test_user_id"),
+                    user_login: str_field("user", "test_user"),
+                    display_name: str_field("user", "test_user"),
+                    kind: str_field("kind", "sub_anniversary").parse().ok()?,
+                    value: data.get("value").and_then(|v| v.as_i64()).unwrap_or(1) as i32,
+                    celebrated_at: Utc::now(),
+                },
+            )),
+            "obs.scene_changed" => Some(BotEvent::ObsSceneChanged {
+                instance: data.get("instance").and_then(|v| v.as_u64()).unwrap_or(1) as u32,
+                scene: str_field("scene", "Main"),
+                timestamp: Utc::now(),
+            }),
+            "heart_rate" => Some(BotEvent::HeartRate {
+                source: str_field("source", "pulsoid"),
+                bpm: data.get("bpm")
+                    .and_then(|v| v.as_u64())
+                    .and_then(|v| u16::try_from(v).ok())
+                    .unwrap_or(80),
+                timestamp: Utc::now(),
+            }),
+            "vrchat.friend_joined" | "vrchat.friend_left" => Some(BotEvent::VRChatPresence {
+                joined: event_type == "vrchat.friend_joined",
+                user_id: str_field("user_id", "usr_test"),
+                display_name: str_field("display_name", "test_friend"),
+                location: str_field("location", "wrld_test:12345"),
+                watched: data.get("watched").and_then(|v| v.as_bool()).unwrap_or(false),
+                timestamp: Utc::now(),
+            }),
+            "twitch.chatters_changed" => {
+                let chatter = |login: String| Chatter {
+                    user_id: format!("test_{}", login),
+                    user_name: login.clone(),
+                    user_login: login,
+                };
+                Some(BotEvent::TwitchChatters {
+                    channel: str_field("channel", "test_channel"),
+                    joined: vec![chatter(str_field("user", "test_user"))],
+                    left: Vec::new(),
+                    present: data.get("present").and_then(|v| v.as_u64()).unwrap_or(1) as usize,
+                    timestamp: Utc::now(),
+                })
+            }
+            "twitch.schedule_starting_soon" => Some(BotEvent::TwitchScheduleStartingSoon {
+                channel: str_field("channel", "test_channel"),
+                segment_id: str_field("segment_id", "test_segment"),
+                title: str_field("title", "Test stream"),
+                category: str_field("category", "Just Chatting"),
+                start_time: Utc::now() + chrono::Duration::minutes(10),
+                timestamp: Utc::now(),
+            }),
+            "twitch.hype_moment" => Some(BotEvent::HypeMoment {
+                channel: str_field("channel", "test_channel"),
+                kind: HypeKind::from_name(&str_field("kind", "chat_surge")).unwrap_or(HypeKind::ChatSurge),
+                score: data.get("score").and_then(|v| v.as_f64()).unwrap_or(1.5),
+                messages: data.get("messages").and_then(|v| v.as_u64()).unwrap_or(60) as u32,
+                emote_messages: data.get("emote_messages").and_then(|v| v.as_u64()).unwrap_or(20) as u32,
+                bits: data.get("bits").and_then(|v| v.as_u64()).unwrap_or(0),
+                subs: data.get("subs").and_then(|v| v.as_u64()).unwrap_or(0),
+                started_at: Utc::now() - chrono::Duration::seconds(30),
+                timestamp: Utc::now(),
+            }),
+            "responder.triggered" => Some(BotEvent::ResponderTriggered {
+                platform: str_field("platform", "twitch-irc"),
+                channel: str_field("channel", "test_channel"),
+                user: str_field("user", "test_user"),
+                platform_user_id: str_field("platform_user_id", "test_user_id"),
+                responder: str_field("responder", "test_responder"),
+                matched: str_field("matched", "hello"),
+                text: str_field("text", "hello there"),
+                response: data.get("response").and_then(|v| v.as_str()).map(String::from),
+                timestamp: Utc::now(),
+            }),
+            "redeem.approval" => Some(BotEvent::RedeemApproval {
+                approval_id: uuid::Uuid::new_v4(),
+                status: str_field("status", "pending").parse().unwrap_or(ApprovalStatus::Pending),
+                reward_name: str_field("reward_name", "Test reward"),
+                channel: str_field("channel", "test_channel"),
+                user: str_field("user", "test_user"),
+                user_input: str_field("user_input", ""),
+                decided_by: data.get("decided_by").and_then(|v| v.as_str()).map(String::from),
+                timestamp: Utc::now(),
+            }),
+            "moderation.escalated" => Some(BotEvent::ModerationEscalated {
+                user_id: uuid::Uuid::new_v4(),
+                user: str_field("user", "test_user"),
+                channel: str_field("channel", "test_channel"),
+                category: str_field("category", "spam"),
+                rule: str_field("rule", "test_rule"),
+                offense: data.get("offense").and_then(|v| v.as_i64()).unwrap_or(2),
+                action: str_field("action", "timeout:600"),
+                timestamp: Utc::now(),
+            }),
+            "ops.alert" => Some(BotEvent::OpsAlert {
+                kind: str_field("kind", "platform_disconnect").parse().unwrap_or(AlertKind::PlatformDisconnect),
+                severity: str_field("severity", "warning").parse().unwrap_or(AlertSeverity::Warning),
+                key: str_field("key", "test"),
+                title: str_field("title", "Test alert"),
+                message: str_field("message", ""),
+                desktop: data.get("desktop").and_then(|v| v.as_bool()).unwrap_or(false),
+                timestamp: Utc::now(),
+            }),
+            "crowd.vote_decided" => Some(BotEvent::CrowdVoteDecided {
+                channel: str_field("channel", "test_channel"),
+                title: str_field("title", "Next effect"),
+                winner: str_field("winner", "Confetti"),
+                control: data.get("control").and_then(|v| v.as_str()).map(String::from),
+                weight: data.get("weight").and_then(|v| v.as_u64()).unwrap_or(3),
+                voters: data.get("voters").and_then(|v| v.as_u64()).unwrap_or(2) as u32,
+                timestamp: Utc::now(),
+            }),
+            other if VRChatGroupEventKind::from_event_type(other).is_some() => Some(BotEvent::VRChatGroup {
+                kind: VRChatGroupEventKind::from_event_type(other)?,
+                group_id: str_field("group_id", "grp_test"),
+                user_id: Some(str_field("user_id", "usr_test")),
+                display_name: Some(str_field("display_name", "test_user")),
+                title: data.get("title").and_then(|v| v.as_str()).map(String::from),
+                timestamp: Utc::now(),
+            }),
+            other if other.starts_with("kick.") => crate::platforms::kick::events::parse_kick_event(other, data)
+                .map(BotEvent::Kick),
+            other => crate::platforms::twitch_eventsub::events::parse_twitch_notification(other, data)
+                .map(BotEvent::TwitchEventSub),
+        }
+    }
+
     /// Get the platform for this event
     pub fn platform(&self) -> Option<Platform> {
         match self {
             BotEvent::ChatMessage { platform, .. } => Some(Platform::from_string(platform)),
             BotEvent::TwitchEventSub(_) => Some(Platform::TwitchEventSub),
+            BotEvent::Kick(_) => Some(Platform::Kick),
+            BotEvent::CredentialRefreshFailed { platform, .. } => Some(Platform::from_string(platform)),
+            BotEvent::ResponderTriggered { platform, .. } => Some(Platform::from_string(platform)),
+            BotEvent::PlatformConnectionChanged { platform, .. } => Some(Platform::from_string(platform)),
+            BotEvent::VRChatPresence { .. } | BotEvent::VRChatGroup { .. } => Some(Platform::VRChat),
+            BotEvent::TwitchChatters { .. }
+            | BotEvent::TwitchScheduleStartingSoon { .. }
+            | BotEvent::HypeMoment { .. }
+            | BotEvent::RedeemApproval { .. }
+            | BotEvent::ModerationEscalated { .. }
+            | BotEvent::CrowdVoteDecided { .. }
+            | BotEvent::ViewerMilestone(_) => Some(Platform::Twitch),
+            _ => None,
+        }
+    }
+
+    /// The platform's id for a chat message (the Twitch `id` tag, the Discord
+    /// message id), if it has one.
+    pub fn message_id(&self) -> Option<&str> {
+        self.chat_metadata_str("message_id")
+    }
+
+    /// For a chat message that replies to another, the parent message's id.
+    pub fn reply_parent_id(&self) -> Option<&str> {
+        self.chat_metadata_str("reply_parent_msg_id")
+    }
+
+    fn chat_metadata_str(&self, key: &str) -> Option<&str> {
+        match self {
+            BotEvent::ChatMessage { metadata, .. } => metadata.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty()),
             _ => None,
         }
     }
@@ -151,11 +703,56 @@ pub struct EventBus {
     subscribers: Arc<Mutex<Vec<mpsc::Sender<BotEvent>>>>,
     shutdown_tx: watch::Sender<bool>,
     pub shutdown_rx: watch::Receiver<bool>,
+    pub metrics: Arc<EventBusMetrics>,
+}
+
+/// Counters for everything published on the bus. Totals are monotonic, so
+/// clients compute events/sec by diffing two snapshots.
+#[derive(Debug)]
+pub struct EventBusMetrics {
+    started_at: Instant,
+    total_events: AtomicU64,
+    counts_by_type: std::sync::Mutex<HashMap<String, u64>>,
+}
+
+impl EventBusMetrics {
+    fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            total_events: AtomicU64::new(0),
+            counts_by_type: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn record(&self, event: &BotEvent) {
+        self.total_events.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut counts) = self.counts_by_type.lock() {
+            *counts.entry(event.event_type()).or_insert(0) += 1;
+        }
+    }
+
+    pub fn uptime_seconds(&self) -> u64 {
+        self.started_at.elapsed().as_secs()
+    }
+
+    pub fn total_events(&self) -> u64 {
+        self.total_events.load(Ordering::Relaxed)
+    }
+
+    pub fn counts_by_type(&self) -> HashMap<String, u64> {
+        self.counts_by_type.lock().map(|c| c.clone()).unwrap_or_default()
+    }
 }
 
 /// Default size for each subscriber’s buffer. Adjust as needed.
 const DEFAULT_BUFFER_SIZE: usize = 10000;
 
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventBus {
     /// Create a new, empty event bus.
     pub fn new() -> Self {
@@ -164,6 +761,7 @@ impl EventBus {
             subscribers: Arc::new(Mutex::new(vec![])),
             shutdown_tx: tx,
             shutdown_rx: rx,
+            metrics: Arc::new(EventBusMetrics::new()),
         }
     }
 
@@ -187,6 +785,7 @@ impl EventBus {
 
     /// Publish an event to all subscribers.
     pub async fn publish(&self, event: BotEvent) {
+        self.metrics.record(&event);
         let senders = {
             let subs = self.subscribers.lock().await;
             subs.clone()
@@ -313,4 +912,36 @@ mod tests {
             panic!("Second message mismatch");
         }
     }
+
+    #[tokio::test]
+    async fn test_metrics_count_published_events() {
+        let bus = EventBus::new();
+
+        bus.publish(BotEvent::Tick).await;
+        bus.publish(BotEvent::Tick).await;
+        bus.publish(BotEvent::SystemMessage("hi".into())).await;
+
+        assert_eq!(bus.metrics.total_events(), 3);
+        let counts = bus.metrics.counts_by_type();
+        assert_eq!(counts.get("tick"), Some(&2));
+        assert_eq!(counts.get("system_message"), Some(&1));
+    }
+
+    #[test]
+    fn test_chat_messages_expose_reply_parents() {
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("message_id".into(), "m2".into());
+        metadata.insert("reply_parent_msg_id".into(), "m1".into());
+        let reply = BotEvent::ChatMessage {
+            platform: "discord".into(),
+            channel: "general".into(),
+            user: "u".into(),
+            text: "same".into(),
+            timestamp: Utc::now(),
+            metadata,
+        };
+        assert_eq!(reply.message_id(), Some("m2"));
+        assert_eq!(reply.reply_parent_id(), Some("m1"));
+        assert_eq!(BotEvent::Tick.reply_parent_id(), None);
+    }
 }
@@ -48,6 +48,12 @@ pub struct DefaultHttpClient {
     client: reqwest::Client,
 }
 
+impl Default for DefaultHttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DefaultHttpClient {
     pub fn new() -> Self {
         Self {
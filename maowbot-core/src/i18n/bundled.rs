@@ -0,0 +1,41 @@
+// File: maowbot-core/src/i18n/bundled.rs
+//
+// Language packs shipped with the bot (maowbot-core/locales/*.json). English is
+// the reference pack: every key exists in it, the others may leave keys out.
+
+use std::collections::HashMap;
+use once_cell::sync::Lazy;
+
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// key -> template
+pub type Pack = HashMap<String, String>;
+
+const PACKS: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.json")),
+    ("es", include_str!("../../locales/es.json")),
+    ("de", include_str!("../../locales/de.json")),
+];
+
+static BUNDLED: Lazy<HashMap<&'static str, Pack>> = Lazy::new(|| {
+    PACKS.iter()
+        .map(|(language, json)| {
+            let pack = serde_json::from_str(json)
+                .unwrap_or_else(|e| panic!("bundled language pack '{}' is invalid: {}", language, e));
+            (*language, pack)
+        })
+        .collect()
+});
+
+pub fn pack(language: &str) -> Option<&'static Pack> {
+    BUNDLED.get(language)
+}
+
+pub fn languages() -> impl Iterator<Item = &'static str> {
+    PACKS.iter().map(|(language, _)| *language)
+}
+
+/// The reference pack, listing every key.
+pub fn english() -> &'static Pack {
+    &BUNDLED[FALLBACK_LANGUAGE]
+}
@@ -0,0 +1,368 @@
+// File: maowbot-core/src/i18n/mod.rs
+//
+// Localized bot responses. Built-in commands, command errors and chat alerts
+// look their text up by key in the channel's language: templates stored in the
+// database first, then the bundled pack, then the base language ("pt" for
+// "pt-br"), and English last. Templates use `{name}` placeholders.
+
+pub mod bundled;
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use parking_lot::RwLock;
+use tracing::info;
+
+use maowbot_common::models::localization::ChannelLanguage;
+use maowbot_common::traits::repository_traits::LocalizationRepository;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+pub use bundled::FALLBACK_LANGUAGE;
+
+/// Fills `{name}` placeholders from `args`. Unknown placeholders are left as written.
+pub fn render(template: &str, args: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').map(|end| (&after[..end], end)) {
+            Some((name, end)) => {
+                match args.iter().find(|(n, _)| *n == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The placeholder names used in a template.
+pub fn placeholders(template: &str) -> BTreeSet<&str> {
+    template.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .collect()
+}
+
+/// Lowercases a language tag and checks it looks like "en" or "pt-br".
+pub fn normalize_language(tag: &str) -> Result<String, Error> {
+    let tag = tag.trim().to_lowercase().replace('_', "-");
+    let mut parts = tag.split('-');
+    let primary_ok = parts.next().is_some_and(|p| (2..=3).contains(&p.len()) && p.chars().all(|c| c.is_ascii_lowercase()));
+    if !primary_ok || !parts.all(|p| (2..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric())) {
+        return Err(Error::Parse(format!("Invalid language '{}', expected e.g. en, es or pt-br", tag)));
+    }
+    Ok(tag)
+}
+
+/// Channels are stored lowercase and without '#'.
+pub fn normalize_channel(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Languages tried for `language`, most specific first, ending in English.
+fn fallback_chain(language: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut tag = language;
+    loop {
+        chain.push(tag.to_string());
+        match tag.rfind('-') {
+            Some(i) => tag = &tag[..i],
+            None => break,
+        }
+    }
+    if !chain.iter().any(|l| l == FALLBACK_LANGUAGE) {
+        chain.push(FALLBACK_LANGUAGE.to_string());
+    }
+    chain
+}
+
+/// The bundled English text, for callers without a `Localizer`.
+pub fn english(key: &str, args: &[(&str, &str)]) -> String {
+    match bundled::english().get(key) {
+        Some(template) => render(template, args),
+        None => key.to_string(),
+    }
+}
+
+/// The text for `key` in a channel, from the localizer when there is one.
+pub fn text_for(
+    localizer: Option<&Localizer>,
+    platform: &str,
+    channel: &str,
+    key: &str,
+    args: &[(&str, &str)],
+) -> String {
+    match localizer {
+        Some(localizer) => localizer.text(platform, channel, key, args),
+        None => english(key, args),
+    }
+}
+
+/// Where the text used for a key in some language comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Stored in the database
+    Custom,
+    /// From the language's bundled pack
+    Bundled,
+    /// Missing; a fallback language is used
+    Fallback,
+}
+
+/// One message key as seen in one language.
+#[derive(Debug, Clone)]
+pub struct MessageEntry {
+    pub key: String,
+    pub english: String,
+    /// The text used in the language, possibly from a fallback
+    pub template: String,
+    pub source: TemplateSource,
+}
+
+/// A language with bundled or stored templates.
+#[derive(Debug, Clone)]
+pub struct LanguageSummary {
+    pub language: String,
+    pub bundled: bool,
+    /// Templates stored in the database
+    pub custom_strings: usize,
+    /// Keys with text of their own, out of every English key
+    pub translated: usize,
+}
+
+pub struct Localizer {
+    repo: Arc<dyn LocalizationRepository>,
+    settings: Arc<SettingsRegistry>,
+    /// language -> key -> template, from the database
+    custom: RwLock<HashMap<String, HashMap<String, String>>>,
+    /// (platform, channel) -> language
+    channels: RwLock<HashMap<(String, String), String>>,
+}
+
+impl Localizer {
+    pub fn new(repo: Arc<dyn LocalizationRepository>, settings: Arc<SettingsRegistry>) -> Self {
+        Self {
+            repo,
+            settings,
+            custom: RwLock::new(HashMap::new()),
+            channels: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Reads stored templates and channel languages into memory.
+    pub async fn load(&self) -> Result<(), Error> {
+        let mut custom: HashMap<String, HashMap<String, String>> = HashMap::new();
+        for s in self.repo.list_strings().await? {
+            custom.entry(s.language).or_default().insert(s.key, s.template);
+        }
+        let channels = self.repo.list_channel_languages().await?
+            .into_iter()
+            .map(|c| ((c.platform, c.channel), c.language))
+            .collect();
+        let count: usize = custom.values().map(HashMap::len).sum();
+        *self.custom.write() = custom;
+        *self.channels.write() = channels;
+        info!("[i18n] loaded {} stored template(s)", count);
+        Ok(())
+    }
+
+    /// The default language from `i18n.default_language`, or English.
+    pub fn default_language(&self) -> String {
+        self.settings.get("i18n.default_language")
+            .and_then(|l| normalize_language(&l).ok())
+            .unwrap_or_else(|| FALLBACK_LANGUAGE.to_string())
+    }
+
+    /// The language a channel is answered in.
+    pub fn language_for(&self, platform: &str, channel: &str) -> String {
+        let key = (platform.to_lowercase(), normalize_channel(channel));
+        self.channels.read().get(&key).cloned().unwrap_or_else(|| self.default_language())
+    }
+
+    /// The text for `key` in the channel's language.
+    pub fn text(&self, platform: &str, channel: &str, key: &str, args: &[(&str, &str)]) -> String {
+        self.text_in(&self.language_for(platform, channel), key, args)
+    }
+
+    /// The text for `key` in `language`, falling back towards English. An
+    /// unknown key is returned as is.
+    pub fn text_in(&self, language: &str, key: &str, args: &[(&str, &str)]) -> String {
+        for lang in fallback_chain(language) {
+            if let Some(template) = self.own_template(&lang, key) {
+                return render(&template, args);
+            }
+        }
+        key.to_string()
+    }
+
+    /// The template `language` has for `key` itself, without fallbacks.
+    fn own_template(&self, language: &str, key: &str) -> Option<String> {
+        if let Some(template) = self.custom.read().get(language).and_then(|m| m.get(key)) {
+            return Some(template.clone());
+        }
+        bundled::pack(language).and_then(|p| p.get(key)).cloned()
+    }
+
+    /// Sets (or with `None`, clears) the language of a channel.
+    pub async fn set_channel_language(&self, platform: &str, channel: &str, language: Option<&str>) -> Result<(), Error> {
+        let platform = platform.trim().to_lowercase();
+        let channel = normalize_channel(channel);
+        if platform.is_empty() || channel.is_empty() {
+            return Err(Error::Parse("Platform and channel are required".into()));
+        }
+        match language {
+            Some(language) => {
+                let language = normalize_language(language)?;
+                self.repo.set_channel_language(&ChannelLanguage {
+                    platform: platform.clone(),
+                    channel: channel.clone(),
+                    language: language.clone(),
+                }).await?;
+                self.channels.write().insert((platform, channel), language);
+            }
+            None => {
+                self.repo.clear_channel_language(&platform, &channel).await?;
+                self.channels.write().remove(&(platform, channel));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn channel_languages(&self) -> Vec<ChannelLanguage> {
+        let mut list: Vec<ChannelLanguage> = self.channels.read().iter()
+            .map(|((platform, channel), language)| ChannelLanguage {
+                platform: platform.clone(),
+                channel: channel.clone(),
+                language: language.clone(),
+            })
+            .collect();
+        list.sort_by(|a, b| (&a.platform, &a.channel).cmp(&(&b.platform, &b.channel)));
+        list
+    }
+
+    /// Stores templates for `language`. Every key must exist in English and
+    /// use only the placeholders the English text has; nothing is stored if
+    /// one doesn't.
+    pub async fn set_strings(&self, language: &str, strings: &[(String, String)]) -> Result<usize, Error> {
+        let language = normalize_language(language)?;
+        for (key, template) in strings {
+            let english = bundled::english().get(key)
+                .ok_or_else(|| Error::Parse(format!("Unknown message key '{}'", key)))?;
+            let allowed = placeholders(english);
+            if let Some(extra) = placeholders(template).into_iter().find(|p| !allowed.contains(p)) {
+                return Err(Error::Parse(format!(
+                    "'{}' has no placeholder {{{}}} (available: {})",
+                    key, extra, allowed.iter().map(|p| format!("{{{}}}", p)).collect::<Vec<_>>().join(", ")
+                )));
+            }
+        }
+        self.repo.upsert_strings(&language, strings).await?;
+        let mut custom = self.custom.write();
+        let pack = custom.entry(language).or_default();
+        for (key, template) in strings {
+            pack.insert(key.clone(), template.clone());
+        }
+        Ok(strings.len())
+    }
+
+    /// Removes a stored template, so the bundled or fallback text is used again.
+    pub async fn delete_string(&self, language: &str, key: &str) -> Result<bool, Error> {
+        let language = normalize_language(language)?;
+        let removed = self.repo.delete_string(&language, key).await?;
+        if let Some(pack) = self.custom.write().get_mut(&language) {
+            pack.remove(key);
+        }
+        Ok(removed)
+    }
+
+    /// Bundled languages and languages with stored templates.
+    pub fn languages(&self) -> Vec<LanguageSummary> {
+        let custom = self.custom.read();
+        let mut names: BTreeSet<String> = bundled::languages().map(String::from).collect();
+        names.extend(custom.keys().cloned());
+        names.into_iter()
+            .map(|language| {
+                let stored = custom.get(&language);
+                let bundled_pack = bundled::pack(&language);
+                let translated = bundled::english().keys()
+                    .filter(|k| stored.is_some_and(|m| m.contains_key(*k)) || bundled_pack.is_some_and(|p| p.contains_key(*k)))
+                    .count();
+                LanguageSummary {
+                    bundled: bundled_pack.is_some(),
+                    custom_strings: stored.map_or(0, HashMap::len),
+                    translated,
+                    language,
+                }
+            })
+            .collect()
+    }
+
+    /// Every message key with the text `language` uses for it, sorted by key.
+    pub fn entries(&self, language: &str) -> Result<Vec<MessageEntry>, Error> {
+        let language = normalize_language(language)?;
+        let custom = self.custom.read();
+        let mut entries: Vec<MessageEntry> = bundled::english().iter()
+            .map(|(key, english)| {
+                let stored = custom.get(&language).and_then(|m| m.get(key));
+                let bundled_text = bundled::pack(&language).and_then(|p| p.get(key));
+                let (template, source) = match (stored, bundled_text) {
+                    (Some(t), _) => (t.clone(), TemplateSource::Custom),
+                    (None, Some(t)) => (t.clone(), TemplateSource::Bundled),
+                    (None, None) => (String::new(), TemplateSource::Fallback),
+                };
+                MessageEntry { key: key.clone(), english: english.clone(), template, source }
+            })
+            .collect();
+        drop(custom);
+        for entry in entries.iter_mut().filter(|e| e.source == TemplateSource::Fallback) {
+            entry.template = self.text_in(&language, &entry.key, &[]);
+        }
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_placeholders() {
+        assert_eq!(render("Wait {seconds}s, {user}.", &[("user", "kitty"), ("seconds", "5")]), "Wait 5s, kitty.");
+        assert_eq!(render("{missing} and {", &[]), "{missing} and {");
+        assert_eq!(placeholders("{a} {b}{a} {not a name}").into_iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_falls_back_towards_english() {
+        assert_eq!(normalize_language(" PT_br ").unwrap(), "pt-br");
+        assert!(normalize_language("english!").is_err());
+        assert_eq!(fallback_chain("pt-br"), vec!["pt-br", "pt", "en"]);
+        assert_eq!(fallback_chain("en"), vec!["en"]);
+    }
+
+    #[test]
+    fn test_bundled_packs_match_english() {
+        let english = bundled::english();
+        for language in bundled::languages() {
+            for (key, template) in bundled::pack(language).unwrap() {
+                let reference = english.get(key)
+                    .unwrap_or_else(|| panic!("'{}' pack has unknown key '{}'", language, key));
+                assert!(
+                    placeholders(template).is_subset(&placeholders(reference)),
+                    "'{}' pack uses a placeholder English doesn't have in '{}'", language, key
+                );
+            }
+        }
+    }
+}
@@ -11,6 +11,10 @@ pub mod plugins;
 pub mod eventbus;
 pub mod cache;
 pub mod services;
+pub mod settings;
+pub mod i18n;
+pub mod updater;
+pub mod disk;
 pub mod test_utils;
 
 pub use db::Database;
@@ -43,7 +43,6 @@ impl DiscordAuthenticator {
         struct DiscordUser {
             id: String,
             username: String,
-            discriminator: String,
         }
         let client = Client::new();
         let resp = client
@@ -148,7 +147,7 @@ impl PlatformAuthenticator for DiscordAuthenticator {
 
                 // 3) Grab the "bot_app_id" from keys and store it in `refresh_token`.
                 //    This is new: we place the app_id in the refresh_token field.
-                let app_id = keys.get("bot_app_id").map(|s| s.clone());
+                let app_id = keys.get("bot_app_id").cloned();
 
                 // 4) Build the final credential object
                 Ok(PlatformCredential {
@@ -23,7 +23,7 @@ use twilight_http::client::ClientBuilder;
 use twilight_http::Client as HttpClient;
 use twilight_model::{
     channel::ChannelType,
-    gateway::payload::incoming::{InteractionCreate, MessageCreate, Ready as ReadyPayload, PresenceUpdate},
+    gateway::payload::incoming::{MessageCreate, Ready as ReadyPayload},
     gateway::presence::ActivityType,
     id::marker::{ApplicationMarker, ChannelMarker, GuildMarker, RoleMarker, UserMarker},
 };
@@ -35,15 +35,48 @@ use maowbot_common::traits::platform_traits::{ConnectionStatus, PlatformAuth, Pl
 use crate::eventbus::EventBus;
 use crate::services::discord::slashcommands;
 
-/// Represents inbound chat message data (not slash commands).
+/// A slash command waiting for its answer; see `DiscordPlatform::finish_interaction`.
 #[derive(Debug, Clone)]
+pub struct DiscordSlashCommand {
+    pub name: String,
+    /// Option values by option name
+    pub options: Vec<(String, String)>,
+    pub interaction_token: String,
+}
+
+/// Represents inbound chat message data, or a slash command when
+/// `slash_command` is set.
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct DiscordMessageEvent {
     pub channel: String,
     pub user_id: String,
     pub username: String,
     pub text: String,
+    #[serde(default)]
     pub user_roles: Vec<String>,
+    #[serde(default)]
     pub guild_id: Option<String>,
+    #[serde(default)]
+    pub message_id: Option<String>,
+    /// The message this one replies to
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(skip)]
+    pub slash_command: Option<DiscordSlashCommand>,
+}
+
+impl DiscordMessageEvent {
+    /// Extra metadata passed to the message service alongside the text.
+    pub fn metadata(&self) -> Vec<String> {
+        [
+            ("guild_id", &self.guild_id),
+            ("message_id", &self.message_id),
+            ("reply_parent_msg_id", &self.reply_to),
+        ]
+            .into_iter()
+            .filter_map(|(key, value)| value.as_ref().map(|v| format!("{}:{}", key, v)))
+            .collect()
+    }
 }
 
 /// The shard runner reads gateway events and updates the cache.
@@ -51,7 +84,7 @@ async fn shard_runner(
     mut shard: Shard,
     tx: UnboundedSender<DiscordMessageEvent>,
     http: Arc<HttpClient>,
-    event_bus: Option<Arc<EventBus>>,
+    _event_bus: Option<Arc<EventBus>>,
     cache: Arc<InMemoryCache>,
     application_id: Option<twilight_model::id::Id<ApplicationMarker>>,
     discord_repo: Option<Arc<dyn maowbot_common::traits::repository_traits::DiscordRepository + Send + Sync>>,
@@ -162,19 +195,21 @@ async fn shard_runner(
                             text: msg.content.clone(),
                             user_roles,
                             guild_id,
+                            message_id: Some(msg.id.to_string()),
+                            reply_to: msg.reference.as_ref()
+                                .and_then(|r| r.message_id)
+                                .map(|id| id.to_string()),
+                            slash_command: None,
                         });
                     }
                     Event::InteractionCreate(inter_create) => {
                         if let Some(app_id) = application_id {
-                            // Dispatch slash command
-                            if let Err(e) = slashcommands::handle_interaction_create(
-                                http.clone(),
-                                app_id,
-                                inter_create,
-                            )
-                                .await
-                            {
-                                error!("Slash command error => {e:?}");
+                            match slashcommands::acknowledge_slash_command(&http, app_id, inter_create).await {
+                                Ok(Some(slash_event)) => {
+                                    let _ = tx.send(slash_event);
+                                }
+                                Ok(None) => {}
+                                Err(e) => error!("Slash command error => {e:?}"),
                             }
                         }
                     }
@@ -208,7 +243,7 @@ async fn shard_runner(
                                     
                                     let is_streaming = presence_update.activities.iter().any(|activity| {
                                         let streaming = activity.kind == ActivityType::Streaming && 
-                                            activity.url.as_ref().map_or(false, |url| url.contains("twitch.tv"));
+                                            activity.url.as_ref().is_some_and(|url| url.contains("twitch.tv"));
                                         
                                         if streaming {
                                             debug!("Found Twitch streaming activity for user {}", user_id);
@@ -245,7 +280,7 @@ async fn shard_runner(
                                     let has_role = if let Some(member) = cache.member(guild_id, user_id) {
                                         // Log roles for debugging
                                         trace!("User {} has {} roles in cache", user_id, member.roles().len());
-                                        let has_live_role = member.roles().iter().any(|&r| r == role_id);
+                                        let has_live_role = member.roles().contains(&role_id);
                                         trace!("User {} has live role: {}", user_id, has_live_role);
                                         has_live_role
                                     } else {
@@ -603,10 +638,12 @@ impl PlatformIntegration for DiscordPlatform {
         let config = Config::new(
             self.token.clone(),
             Intents::GUILDS | Intents::GUILD_MESSAGES | Intents::MESSAGE_CONTENT | 
-            Intents::GUILD_PRESENCES | Intents::GUILD_MEMBERS | Intents::GUILD_VOICE_STATES,
+            Intents::GUILD_PRESENCES | Intents::GUILD_MEMBERS | Intents::GUILD_VOICE_STATES |
+            // DMs carry `!link` codes
+            Intents::DIRECT_MESSAGES,
         );
         
-        info!("Configuring Discord gateway with intents: GUILDS | GUILD_MESSAGES | MESSAGE_CONTENT | GUILD_PRESENCES | GUILD_MEMBERS | GUILD_VOICE_STATES");
+        info!("Configuring Discord gateway with intents: GUILDS | GUILD_MESSAGES | MESSAGE_CONTENT | GUILD_PRESENCES | GUILD_MEMBERS | GUILD_VOICE_STATES | DIRECT_MESSAGES");
 
         let shards = gateway::create_recommended(&http_client, config, |_, b| b.build())
             .await
@@ -664,8 +701,60 @@ impl PlatformIntegration for DiscordPlatform {
         Ok(())
     }
 
-    /// For 0.16, `.content(...)` is not a `Result`. No `?` needed.
     async fn send_message(&self, channel: &str, message: &str) -> Result<(), Error> {
+        self.create_message(channel, message, None).await
+    }
+
+    async fn get_connection_status(&self) -> Result<ConnectionStatus, Error> {
+        Ok(self.connection_status.clone())
+    }
+}
+
+impl DiscordPlatform {
+    /// Replies to the message with id `reply_to` in `channel` (a channel ID).
+    pub async fn send_reply(&self, channel: &str, reply_to: &str, message: &str) -> Result<(), Error> {
+        let reply_to: u64 = reply_to.parse().map_err(|_| {
+            Error::Platform(format!("Invalid message ID: {}", reply_to))
+        })?;
+        self.create_message(channel, message, Some(reply_to)).await
+    }
+
+    /// Sends `message` as a direct message to the user with id `user_id`.
+    /// Fails when the user doesn't share a server with the bot or blocks DMs.
+    pub async fn send_direct_message(&self, user_id: &str, message: &str) -> Result<(), Error> {
+        let user_id_u64: u64 = user_id.parse().map_err(|_| {
+            Error::Platform(format!("Invalid user ID: {}", user_id))
+        })?;
+        let Some(http) = &self.http else {
+            return Err(Error::Platform("Discord is not connected".into()));
+        };
+        let channel = http.create_private_channel(twilight_model::id::Id::<UserMarker>::new(user_id_u64))
+            .await
+            .map_err(|e| Error::Platform(format!("Failed to open a DM with {}: {e}", user_id)))?
+            .model()
+            .await
+            .map_err(|e| Error::Platform(format!("Failed to read the DM channel: {e}")))?;
+        self.create_message(&channel.id.get().to_string(), message, None).await
+    }
+
+    /// Answers a slash command the shard runner acknowledged. Discord keeps
+    /// the interaction token valid for 15 minutes.
+    pub async fn finish_interaction(&self, interaction_token: &str, text: &str) -> Result<(), Error> {
+        let (Some(http), Some(app_id)) = (&self.http, self.application_id) else {
+            return Err(Error::Platform("Discord is not connected".into()));
+        };
+        // Discord's message limit
+        let text: String = text.chars().take(2000).collect();
+        http.interaction(app_id)
+            .update_response(interaction_token)
+            .content(Some(&text))
+            .await
+            .map_err(|e| Error::Platform(format!("Failed to answer slash command: {e}")))?;
+        Ok(())
+    }
+
+    /// For 0.16, `.content(...)` is not a `Result`. No `?` needed.
+    async fn create_message(&self, channel: &str, message: &str, reply_to: Option<u64>) -> Result<(), Error> {
         // Channel must be a numeric ID for Discord API
         if !channel.chars().all(|c| c.is_ascii_digit()) {
             return Err(Error::Platform(format!("Channel must be an ID, but got a name: {}", channel)));
@@ -677,10 +766,14 @@ impl PlatformIntegration for DiscordPlatform {
         let channel_id = twilight_model::id::Id::<ChannelMarker>::new(channel_id_u64);
 
         if let Some(http) = &self.http {
-            http.create_message(channel_id)
-                .content(message)
+            let mut builder = http.create_message(channel_id)
                 // `.content(...)` is not a Result in Twilight 0.16,
                 // so no `.map_err(...)` or `?`.
+                .content(message);
+            if let Some(reply_to) = reply_to {
+                builder = builder.reply(twilight_model::id::Id::new(reply_to));
+            }
+            builder
                 .await
                 .map_err(|e| Error::Platform(format!("Failed to send Discord message: {e}")))?;
         }
@@ -688,12 +781,6 @@ impl PlatformIntegration for DiscordPlatform {
         Ok(())
     }
 
-    async fn get_connection_status(&self) -> Result<ConnectionStatus, Error> {
-        Ok(self.connection_status.clone())
-    }
-}
-
-impl DiscordPlatform {
     /// Add a role to a Discord user
     pub async fn add_role_to_user(
         &self,
@@ -12,6 +12,12 @@ pub struct SongbirdManager {
     // For now, empty
 }
 
+impl Default for SongbirdManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SongbirdManager {
     pub fn new() -> Self {
         Self {}
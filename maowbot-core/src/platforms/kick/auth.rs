@@ -0,0 +1,245 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::Utc;
+use rand::Rng;
+use reqwest::Client as ReqwestClient;
+use ring::digest::{digest, SHA256};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::auth::{AuthenticationPrompt, AuthenticationResponse};
+use maowbot_common::models::credential::CredentialType;
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::traits::auth_traits::PlatformAuthenticator;
+use crate::Error;
+
+use super::client::KickApiClient;
+
+const AUTHORIZE_URL: &str = "https://id.kick.com/oauth/authorize";
+const TOKEN_URL: &str = "https://id.kick.com/oauth/token";
+const REVOKE_URL: &str = "https://id.kick.com/oauth/revoke";
+const REDIRECT_URI: &str = "http://localhost:9876/callback";
+
+const KICK_SCOPES: &[&str] = &["user:read", "channel:read", "chat:write", "events:subscribe"];
+
+static STATE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Deserialize)]
+struct KickTokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    #[serde(alias = "expiry")]
+    expires_in: Option<i64>,
+    scope: Option<String>,
+}
+
+/// Kick uses OAuth 2.1, so every authorization-code login carries a PKCE verifier.
+pub struct KickAuthenticator {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub is_broadcaster: bool,
+    pub is_teammate: bool,
+    pub is_bot: bool,
+    pending_state: Option<String>,
+    pending_verifier: Option<String>,
+}
+
+impl KickAuthenticator {
+    pub fn new(client_id: String, client_secret: Option<String>) -> Self {
+        Self {
+            client_id,
+            client_secret,
+            is_broadcaster: false,
+            is_teammate: false,
+            is_bot: false,
+            pending_state: None,
+            pending_verifier: None,
+        }
+    }
+
+    fn build_auth_url(&self, state: &str, challenge: &str) -> String {
+        format!(
+            "{}?response_type=code&client_id={}&redirect_uri={}&scope={}&code_challenge={}&code_challenge_method=S256&state={}",
+            AUTHORIZE_URL,
+            urlencoding::encode(&self.client_id),
+            urlencoding::encode(REDIRECT_URI),
+            urlencoding::encode(&KICK_SCOPES.join(" ")),
+            urlencoding::encode(challenge),
+            urlencoding::encode(state),
+        )
+    }
+
+    async fn request_token(&self, params: &[(&str, String)]) -> Result<KickTokenResponse, Error> {
+        ReqwestClient::new()
+            .post(TOKEN_URL)
+            .form(params)
+            .send()
+            .await
+            .map_err(|e| Error::Auth(format!("HTTP error calling Kick token endpoint: {e}")))?
+            .error_for_status()
+            .map_err(|e| Error::Auth(format!("Kick token endpoint error: {e}")))?
+            .json::<KickTokenResponse>()
+            .await
+            .map_err(|e| Error::Auth(format!("Parse error on Kick token JSON: {e}")))
+    }
+
+    /// Looks up who the token belongs to and which chatroom their channel uses,
+    /// so the runtime can subscribe without another round-trip.
+    async fn additional_data(&self, access_token: &str, scope: Option<String>) -> Result<(String, String, serde_json::Value), Error> {
+        let api = KickApiClient::new(access_token);
+        let me = api.current_user().await?;
+        let slug = me.name.to_lowercase();
+
+        let mut data = serde_json::json!({
+            "client_id": self.client_id,
+            "scope": scope.unwrap_or_default(),
+            "slug": slug,
+        });
+        match api.channel_by_slug(&slug).await {
+            Ok(channel) => {
+                data["channel_id"] = channel.channel_id.into();
+                data["chatroom_id"] = channel.chatroom_id.into();
+            }
+            Err(e) => warn!("Could not resolve Kick chatroom for '{}' (will retry at connect): {:?}", slug, e),
+        }
+        debug!("KickAuthenticator: token belongs to '{}' (user_id={})", me.name, me.user_id);
+        Ok((me.name, me.user_id.to_string(), data))
+    }
+}
+
+/// Random PKCE verifier plus its S256 challenge.
+fn pkce_pair() -> (String, String) {
+    let bytes: [u8; 32] = rand::rng().random();
+    let verifier = URL_SAFE_NO_PAD.encode(bytes);
+    let challenge = URL_SAFE_NO_PAD.encode(digest(&SHA256, verifier.as_bytes()).as_ref());
+    (verifier, challenge)
+}
+
+#[async_trait]
+impl PlatformAuthenticator for KickAuthenticator {
+    async fn initialize(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn start_authentication(&mut self) -> Result<AuthenticationPrompt, Error> {
+        let c = STATE_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let state = format!("kick-state-{}", c);
+        let (verifier, challenge) = pkce_pair();
+        let url = self.build_auth_url(&state, &challenge);
+        self.pending_state = Some(state);
+        self.pending_verifier = Some(verifier);
+        Ok(AuthenticationPrompt::Browser { url })
+    }
+
+    async fn complete_authentication(
+        &mut self,
+        response: AuthenticationResponse,
+    ) -> Result<PlatformCredential, Error> {
+        let code = match response {
+            AuthenticationResponse::Code(c) => c,
+            _ => return Err(Error::Auth("Expected code in complete_authentication".into())),
+        };
+        let verifier = self.pending_verifier.take()
+            .ok_or_else(|| Error::Auth("No Kick login in progress".into()))?;
+
+        let resp = self.request_token(&[
+            ("grant_type", "authorization_code".to_string()),
+            ("client_id", self.client_id.clone()),
+            ("client_secret", self.client_secret.clone().unwrap_or_default()),
+            ("redirect_uri", REDIRECT_URI.to_string()),
+            ("code_verifier", verifier),
+            ("code", code),
+        ]).await?;
+        self.pending_state = None;
+
+        let (user_name, platform_id, additional_data) =
+            self.additional_data(&resp.access_token, resp.scope).await?;
+        let now = Utc::now();
+
+        Ok(PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::Kick,
+            platform_id: Some(platform_id),
+            credential_type: CredentialType::OAuth2,
+            user_id: Uuid::new_v4(), // Will be updated later
+            user_name,
+            primary_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            additional_data: Some(additional_data),
+            expires_at: resp.expires_in.map(|s| now + chrono::Duration::seconds(s)),
+            created_at: now,
+            updated_at: now,
+            is_bot: self.is_bot,
+            is_teammate: self.is_teammate,
+            is_broadcaster: self.is_broadcaster,
+        })
+    }
+
+    async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
+        let refresh_token = credential.refresh_token.clone()
+            .ok_or_else(|| Error::Auth("No refresh token available.".into()))?;
+
+        let resp = self.request_token(&[
+            ("grant_type", "refresh_token".to_string()),
+            ("client_id", self.client_id.clone()),
+            ("client_secret", self.client_secret.clone().unwrap_or_default()),
+            ("refresh_token", refresh_token),
+        ]).await?;
+
+        let now = Utc::now();
+        let mut additional_data = credential.additional_data.clone().unwrap_or_else(|| serde_json::json!({}));
+        if let Some(scope) = resp.scope {
+            additional_data["scope"] = scope.into();
+        }
+
+        Ok(PlatformCredential {
+            primary_token: resp.access_token,
+            // Kick rotates refresh tokens; keep the old one if none came back
+            refresh_token: resp.refresh_token.or_else(|| credential.refresh_token.clone()),
+            additional_data: Some(additional_data),
+            expires_at: resp.expires_in.map(|s| now + chrono::Duration::seconds(s)),
+            updated_at: now,
+            ..credential.clone()
+        })
+    }
+
+    async fn validate(&self, credential: &PlatformCredential) -> Result<bool, Error> {
+        match KickApiClient::new(&credential.primary_token).current_user().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                error!("Kick token validation failed => {e:?}");
+                Ok(false)
+            }
+        }
+    }
+
+    async fn revoke(&mut self, credential: &PlatformCredential) -> Result<(), Error> {
+        let url = format!(
+            "{}?token={}&token_hint_type=access_token",
+            REVOKE_URL,
+            urlencoding::encode(&credential.primary_token),
+        );
+        ReqwestClient::new()
+            .post(url)
+            .send()
+            .await
+            .map_err(|e| Error::Auth(format!("HTTP error revoking Kick token: {e}")))?
+            .error_for_status()
+            .map_err(|e| Error::Auth(format!("Failed to revoke Kick token: {e}")))?;
+        Ok(())
+    }
+
+    fn set_is_broadcaster(&mut self, val: bool) {
+        self.is_broadcaster = val;
+    }
+
+    fn set_is_teammate(&mut self, val: bool) {
+        self.is_teammate = val;
+    }
+
+    fn set_is_bot(&mut self, val: bool) {
+        self.is_bot = val;
+    }
+}
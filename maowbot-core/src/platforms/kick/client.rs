@@ -0,0 +1,260 @@
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client as ReqwestClient;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::{debug, error, info, trace, warn};
+
+use crate::Error;
+
+const API_BASE: &str = "https://api.kick.com/public/v1";
+/// Chatroom ids are not part of the public API yet, only the site's own v2 endpoint.
+const CHANNEL_LOOKUP_URL: &str = "https://kick.com/api/v2/channels";
+/// Public Pusher app key the kick.com web client uses for chat.
+const DEFAULT_PUSHER_KEY: &str = "32cbd69e4b950bf97679";
+const PUSHER_CLUSTER: &str = "us2";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickUser {
+    pub user_id: u64,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct KickApiResponse<T> {
+    data: T,
+}
+
+/// What the runtime needs to read and write a channel's chat.
+#[derive(Debug, Clone)]
+pub struct KickChannelInfo {
+    pub slug: String,
+    pub channel_id: u64,
+    pub chatroom_id: u64,
+    pub broadcaster_user_id: u64,
+}
+
+#[derive(Deserialize)]
+struct ChannelLookup {
+    id: u64,
+    slug: String,
+    user_id: u64,
+    chatroom: ChatroomLookup,
+}
+
+#[derive(Deserialize)]
+struct ChatroomLookup {
+    id: u64,
+}
+
+/// Thin wrapper over the Kick public REST API.
+pub struct KickApiClient {
+    http: ReqwestClient,
+    access_token: String,
+}
+
+impl KickApiClient {
+    pub fn new(access_token: &str) -> Self {
+        Self {
+            http: ReqwestClient::new(),
+            access_token: access_token.to_string(),
+        }
+    }
+
+    /// The user the access token belongs to.
+    pub async fn current_user(&self) -> Result<KickUser, Error> {
+        let resp: KickApiResponse<Vec<KickUser>> = self.http
+            .get(format!("{API_BASE}/users"))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Platform(format!("Kick /users error: {e}")))?
+            .json()
+            .await?;
+        resp.data.into_iter().next()
+            .ok_or_else(|| Error::Platform("Kick /users returned no user for this token".into()))
+    }
+
+    pub async fn channel_by_slug(&self, slug: &str) -> Result<KickChannelInfo, Error> {
+        let slug = normalize_slug(slug);
+        let lookup: ChannelLookup = self.http
+            .get(format!("{CHANNEL_LOOKUP_URL}/{}", urlencoding::encode(&slug)))
+            .header("Accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Platform(format!("Kick channel lookup for '{slug}' failed: {e}")))?
+            .json()
+            .await?;
+        Ok(KickChannelInfo {
+            slug: lookup.slug,
+            channel_id: lookup.id,
+            chatroom_id: lookup.chatroom.id,
+            broadcaster_user_id: lookup.user_id,
+        })
+    }
+
+    /// Posts a chat message as the token's user into `broadcaster_user_id`'s chat.
+    pub async fn send_chat_message(&self, broadcaster_user_id: u64, content: &str) -> Result<(), Error> {
+        self.http
+            .post(format!("{API_BASE}/chat"))
+            .bearer_auth(&self.access_token)
+            .json(&json!({
+                "broadcaster_user_id": broadcaster_user_id,
+                "content": content,
+                "type": "user",
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Platform(format!("Kick chat send failed: {e}")))?;
+        Ok(())
+    }
+}
+
+/// "#Foo" and "foo" both name the channel with slug "foo".
+pub fn normalize_slug(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// One event pushed on a subscribed Pusher channel.
+#[derive(Debug, Clone)]
+pub struct PusherEvent {
+    pub channel: String,
+    pub event: String,
+    pub data: String,
+}
+
+enum PusherCommand {
+    Subscribe(String),
+    Unsubscribe(String),
+}
+
+/// Read-only connection to Kick's chat websocket (Pusher protocol).
+pub struct KickChatClient {
+    commands: mpsc::UnboundedSender<PusherCommand>,
+    pub incoming: Option<mpsc::Receiver<PusherEvent>>,
+    task: JoinHandle<()>,
+}
+
+impl KickChatClient {
+    pub async fn connect() -> Result<Self, Error> {
+        let key = std::env::var("KICK_PUSHER_KEY").unwrap_or_else(|_| DEFAULT_PUSHER_KEY.to_string());
+        let url = format!(
+            "wss://ws-{PUSHER_CLUSTER}.pusher.com/app/{key}?protocol=7&client=js&version=8.4.0&flash=false"
+        );
+        let (ws, _) = connect_async(&url)
+            .await
+            .map_err(|e| Error::Platform(format!("Kick websocket connect error: {e}")))?;
+        info!("[Kick] websocket connected");
+
+        let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel::<PusherCommand>();
+        let (evt_tx, evt_rx) = mpsc::channel::<PusherEvent>(1000);
+        let (mut sink, mut stream) = ws.split();
+
+        let task = tokio::spawn(async move {
+            // Pusher drops idle sockets after ~120s; ping well before that.
+            let mut keepalive = interval(Duration::from_secs(60));
+            keepalive.tick().await;
+            loop {
+                tokio::select! {
+                    msg = stream.next() => {
+                        let msg = match msg {
+                            Some(Ok(m)) => m,
+                            Some(Err(e)) => {
+                                error!("[Kick] websocket error: {e}");
+                                break;
+                            }
+                            None => break,
+                        };
+                        if msg.is_close() {
+                            break;
+                        }
+                        let Message::Text(txt) = msg else { continue };
+                        let Ok(frame) = serde_json::from_str::<serde_json::Value>(&txt) else {
+                            warn!("[Kick] non-JSON frame: {}", txt);
+                            continue;
+                        };
+                        let event = frame.get("event").and_then(|v| v.as_str()).unwrap_or_default();
+                        match event {
+                            "pusher:ping" => {
+                                let pong = json!({ "event": "pusher:pong", "data": {} }).to_string();
+                                if sink.send(Message::text(pong)).await.is_err() {
+                                    break;
+                                }
+                            }
+                            "pusher:pong" => trace!("[Kick] pong"),
+                            "pusher:connection_established" => debug!("[Kick] connection established"),
+                            "pusher_internal:subscription_succeeded" => {
+                                debug!("[Kick] subscribed to {:?}", frame.get("channel"));
+                            }
+                            "pusher:error" => warn!("[Kick] pusher error: {:?}", frame.get("data")),
+                            _ => {
+                                let data = match frame.get("data") {
+                                    Some(serde_json::Value::String(s)) => s.clone(),
+                                    Some(other) => other.to_string(),
+                                    None => String::new(),
+                                };
+                                let evt = PusherEvent {
+                                    channel: frame.get("channel").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                                    event: event.to_string(),
+                                    data,
+                                };
+                                if evt_tx.send(evt).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    cmd = cmd_rx.recv() => {
+                        let frame = match cmd {
+                            Some(PusherCommand::Subscribe(channel)) => {
+                                json!({ "event": "pusher:subscribe", "data": { "auth": "", "channel": channel } })
+                            }
+                            Some(PusherCommand::Unsubscribe(channel)) => {
+                                json!({ "event": "pusher:unsubscribe", "data": { "channel": channel } })
+                            }
+                            None => break,
+                        };
+                        if sink.send(Message::text(frame.to_string())).await.is_err() {
+                            break;
+                        }
+                    }
+                    _ = keepalive.tick() => {
+                        let ping = json!({ "event": "pusher:ping", "data": {} }).to_string();
+                        if sink.send(Message::text(ping)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            info!("[Kick] websocket closed");
+        });
+
+        Ok(Self {
+            commands: cmd_tx,
+            incoming: Some(evt_rx),
+            task,
+        })
+    }
+
+    /// Subscribes to the chatroom and channel events of `info`.
+    pub fn join(&self, info: &KickChannelInfo) {
+        let _ = self.commands.send(PusherCommand::Subscribe(format!("chatrooms.{}.v2", info.chatroom_id)));
+        let _ = self.commands.send(PusherCommand::Subscribe(format!("channel.{}", info.channel_id)));
+    }
+
+    pub fn leave(&self, info: &KickChannelInfo) {
+        let _ = self.commands.send(PusherCommand::Unsubscribe(format!("chatrooms.{}.v2", info.chatroom_id)));
+        let _ = self.commands.send(PusherCommand::Unsubscribe(format!("channel.{}", info.channel_id)));
+    }
+
+    pub fn shutdown(&self) {
+        self.task.abort();
+    }
+}
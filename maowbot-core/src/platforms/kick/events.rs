@@ -0,0 +1,134 @@
+// File: maowbot-core/src/platforms/kick/events.rs
+//
+// Typed payloads for the Pusher events Kick pushes on `chatrooms.{id}.v2`
+// and `channel.{id}`. Field names follow Kick's JSON.
+
+use serde::{Deserialize, Serialize};
+
+use crate::eventbus::KickEventData;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickBadge {
+    #[serde(rename = "type")]
+    pub badge_type: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct KickIdentity {
+    #[serde(default)]
+    pub badges: Vec<KickBadge>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickSender {
+    pub id: u64,
+    pub username: String,
+    pub slug: String,
+    #[serde(default)]
+    pub identity: KickIdentity,
+}
+
+/// `App\Events\ChatMessageEvent`
+#[derive(Debug, Clone, Deserialize)]
+pub struct KickChatMessage {
+    pub id: String,
+    pub chatroom_id: u64,
+    pub content: String,
+    pub sender: KickSender,
+}
+
+impl KickChatMessage {
+    /// Badge types as roles, e.g. "broadcaster", "moderator", "subscriber".
+    pub fn roles(&self) -> Vec<String> {
+        self.sender.identity.badges.iter().map(|b| b.badge_type.clone()).collect()
+    }
+}
+
+/// `App\Events\FollowersUpdated`; `followed` is false for unfollows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KickFollow {
+    pub channel_id: u64,
+    pub username: Option<String>,
+    #[serde(rename = "followersCount", default)]
+    pub followers_count: u64,
+    #[serde(default)]
+    pub followed: bool,
+}
+
+/// `App\Events\SubscriptionEvent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KickSubscription {
+    pub chatroom_id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub months: u32,
+}
+
+/// `App\Events\GiftedSubscriptionsEvent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KickGiftedSubscriptions {
+    pub chatroom_id: u64,
+    pub gifter_username: String,
+    #[serde(default)]
+    pub gifted_usernames: Vec<String>,
+}
+
+/// Something worth handing to the runtime from a Pusher frame.
+#[derive(Debug, Clone)]
+pub enum KickIncoming {
+    Chat(KickChatMessage),
+    Event(KickEventData),
+}
+
+/// Parses one Pusher event (`data` is the JSON string Pusher wraps payloads in).
+/// Returns None for events we don't handle or payloads that don't match.
+pub fn parse_pusher_event(event: &str, data: &str) -> Option<KickIncoming> {
+    match event {
+        "App\\Events\\ChatMessageEvent" => serde_json::from_str::<KickChatMessage>(data).ok()
+            .map(KickIncoming::Chat),
+        "App\\Events\\FollowersUpdated" => serde_json::from_str::<KickFollow>(data).ok()
+            .filter(|f| f.followed && f.username.is_some())
+            .map(|f| KickIncoming::Event(KickEventData::Follow(f))),
+        "App\\Events\\SubscriptionEvent" => serde_json::from_str::<KickSubscription>(data).ok()
+            .map(|s| KickIncoming::Event(KickEventData::Subscription(s))),
+        "App\\Events\\GiftedSubscriptionsEvent" => serde_json::from_str::<KickGiftedSubscriptions>(data).ok()
+            .map(|g| KickIncoming::Event(KickEventData::GiftedSubscriptions(g))),
+        _ => None,
+    }
+}
+
+/// Builds a Kick event from its bus event type ("kick.follow", ...) and JSON,
+/// for pipeline test-fire and simulation.
+pub fn parse_kick_event(event_type: &str, data: &serde_json::Value) -> Option<KickEventData> {
+    match event_type {
+        "kick.follow" => serde_json::from_value::<KickFollow>(data.clone()).ok()
+            .map(KickEventData::Follow),
+        "kick.subscription" => serde_json::from_value::<KickSubscription>(data.clone()).ok()
+            .map(KickEventData::Subscription),
+        "kick.subscription.gift" => serde_json::from_value::<KickGiftedSubscriptions>(data.clone()).ok()
+            .map(KickEventData::GiftedSubscriptions),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_chat_and_follow_events() {
+        let chat = r##"{"id":"abc","chatroom_id":42,"content":"hi","type":"message",
+            "sender":{"id":7,"username":"Foo","slug":"foo",
+            "identity":{"color":"#fff","badges":[{"type":"moderator","text":"Moderator"}]}}}"##;
+        match parse_pusher_event("App\\Events\\ChatMessageEvent", chat) {
+            Some(KickIncoming::Chat(msg)) => {
+                assert_eq!(msg.sender.username, "Foo");
+                assert_eq!(msg.roles(), vec!["moderator".to_string()]);
+            }
+            other => panic!("expected chat, got {other:?}"),
+        }
+
+        let unfollow = r#"{"followersCount":10,"channel_id":1,"username":"bar","followed":false}"#;
+        assert!(parse_pusher_event("App\\Events\\FollowersUpdated", unfollow).is_none());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod auth;
+pub mod client;
+pub mod events;
+pub mod runtime;
+
+pub use auth::KickAuthenticator;
+pub use runtime::{KickPlatform, KickChatEvent};
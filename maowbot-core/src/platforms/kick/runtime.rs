@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex as AsyncMutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::Error;
+use crate::eventbus::{BotEvent, EventBus};
+use maowbot_common::models::platform::PlatformCredential;
+use maowbot_common::traits::platform_traits::{ChatPlatform, ConnectionStatus, PlatformAuth, PlatformIntegration};
+
+use super::client::{normalize_slug, KickApiClient, KickChannelInfo, KickChatClient};
+use super::events::{parse_pusher_event, KickIncoming};
+
+#[derive(Debug, Clone)]
+pub struct KickChatEvent {
+    /// "#slug" of the channel the message was posted in
+    pub channel: String,
+    /// Kick's numeric user id of the sender
+    pub kick_user_id: String,
+    pub display_name: String,
+    pub text: String,
+    pub roles: Vec<String>,
+}
+
+pub struct KickPlatform {
+    pub credentials: Option<PlatformCredential>,
+    pub connection_status: ConnectionStatus,
+    pub client: Option<KickChatClient>,
+    pub read_loop_handle: Option<JoinHandle<()>>,
+    pub event_bus: Option<Arc<EventBus>>,
+
+    /// Chat messages for the manager to feed through the message service.
+    pub(crate) rx: Option<mpsc::Receiver<KickChatEvent>>,
+
+    /// Joined channels by slug; shared with the read loop to map chatroom ids back.
+    channels: Arc<AsyncMutex<HashMap<String, KickChannelInfo>>>,
+}
+
+impl Default for KickPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KickPlatform {
+    pub fn new() -> Self {
+        Self {
+            credentials: None,
+            connection_status: ConnectionStatus::Disconnected,
+            client: None,
+            read_loop_handle: None,
+            event_bus: None,
+            rx: None,
+            channels: Arc::new(AsyncMutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn set_credentials(&mut self, creds: PlatformCredential) {
+        self.credentials = Some(creds);
+    }
+
+    pub fn set_event_bus(&mut self, bus: Arc<EventBus>) {
+        self.event_bus = Some(bus);
+    }
+
+    fn api(&self) -> Result<KickApiClient, Error> {
+        let creds = self.credentials.as_ref()
+            .ok_or_else(|| Error::Platform("Kick: No credentials set".into()))?;
+        Ok(KickApiClient::new(&creds.primary_token))
+    }
+
+    /// Resolves (and caches) the chatroom for a channel slug.
+    async fn channel_info(&self, channel: &str) -> Result<KickChannelInfo, Error> {
+        let slug = normalize_slug(channel);
+        if let Some(info) = self.channels.lock().await.get(&slug) {
+            return Ok(info.clone());
+        }
+        self.api()?.channel_by_slug(&slug).await
+    }
+
+    /// The account's own channel, using the ids stored at login when present.
+    fn own_channel(creds: &PlatformCredential) -> Option<KickChannelInfo> {
+        let data = creds.additional_data.as_ref()?;
+        Some(KickChannelInfo {
+            slug: data.get("slug")?.as_str()?.to_string(),
+            channel_id: data.get("channel_id")?.as_u64()?,
+            chatroom_id: data.get("chatroom_id")?.as_u64()?,
+            broadcaster_user_id: creds.platform_id.as_deref()?.parse().ok()?,
+        })
+    }
+}
+
+#[async_trait]
+impl PlatformAuth for KickPlatform {
+    async fn authenticate(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    async fn refresh_auth(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+    async fn revoke_auth(&mut self) -> Result<(), Error> {
+        self.credentials = None;
+        Ok(())
+    }
+    async fn is_authenticated(&self) -> Result<bool, Error> {
+        Ok(self.credentials.is_some())
+    }
+}
+
+#[async_trait]
+impl PlatformIntegration for KickPlatform {
+    async fn connect(&mut self) -> Result<(), Error> {
+        if self.client.is_some() {
+            info!("(KickPlatform) connect ⇒ already connected");
+            return Ok(());
+        }
+        let creds = self.credentials.clone()
+            .ok_or_else(|| Error::Platform("Kick: No credentials set".into()))?;
+
+        let mut client = KickChatClient::connect().await?;
+        let mut incoming = client.incoming.take()
+            .ok_or_else(|| Error::Platform("No incoming channel in KickChatClient".into()))?;
+        self.client = Some(client);
+        self.connection_status = ConnectionStatus::Connected;
+
+        let own = match Self::own_channel(&creds) {
+            Some(info) => Some(info),
+            None => self.channel_info(&creds.user_name).await.ok(),
+        };
+        match own {
+            Some(info) => self.join_channel(&info.slug).await?,
+            None => warn!("(KickPlatform) could not resolve own channel for '{}'", creds.user_name),
+        }
+
+        // Sender only lives in the read loop, so `rx` closes when the socket does.
+        let (tx, rx) = mpsc::channel::<KickChatEvent>(1000);
+        self.rx = Some(rx);
+        let channels = self.channels.clone();
+        let event_bus = self.event_bus.clone();
+
+        let handle = tokio::spawn(async move {
+            while let Some(frame) = incoming.recv().await {
+                match parse_pusher_event(&frame.event, &frame.data) {
+                    Some(KickIncoming::Chat(msg)) => {
+                        let slug = channels.lock().await.values()
+                            .find(|c| c.chatroom_id == msg.chatroom_id)
+                            .map(|c| c.slug.clone())
+                            .unwrap_or_else(|| msg.chatroom_id.to_string());
+                        let roles = msg.roles();
+                        let evt = KickChatEvent {
+                            channel: format!("#{slug}"),
+                            kick_user_id: msg.sender.id.to_string(),
+                            display_name: msg.sender.username,
+                            text: msg.content,
+                            roles,
+                        };
+                        if tx.send(evt).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(KickIncoming::Event(data)) => {
+                        if let Some(bus) = &event_bus {
+                            bus.publish(BotEvent::Kick(data)).await;
+                        }
+                    }
+                    None => debug!("(KickPlatform) unhandled event {} on {}", frame.event, frame.channel),
+                }
+            }
+            info!("(KickPlatform) read loop ended.");
+        });
+        self.read_loop_handle = Some(handle);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), Error> {
+        self.connection_status = ConnectionStatus::Disconnected;
+        if let Some(cli) = self.client.take() {
+            cli.shutdown();
+        }
+        if let Some(h) = self.read_loop_handle.take() {
+            h.abort();
+        }
+        Ok(())
+    }
+
+    async fn send_message(&self, channel: &str, message: &str) -> Result<(), Error> {
+        let info = self.channel_info(channel).await?;
+        self.api()?.send_chat_message(info.broadcaster_user_id, message).await
+    }
+
+    async fn get_connection_status(&self) -> Result<ConnectionStatus, Error> {
+        Ok(self.connection_status.clone())
+    }
+}
+
+#[async_trait]
+impl ChatPlatform for KickPlatform {
+    async fn join_channel(&self, channel: &str) -> Result<(), Error> {
+        let cli = self.client.as_ref()
+            .ok_or_else(|| Error::Platform("No active Kick chat connection".into()))?;
+        let info = self.channel_info(channel).await?;
+        cli.join(&info);
+        self.channels.lock().await.insert(info.slug.clone(), info);
+        Ok(())
+    }
+
+    async fn leave_channel(&self, channel: &str) -> Result<(), Error> {
+        let cli = self.client.as_ref()
+            .ok_or_else(|| Error::Platform("No active Kick chat connection".into()))?;
+        if let Some(info) = self.channels.lock().await.remove(&normalize_slug(channel)) {
+            cli.leave(&info);
+        }
+        Ok(())
+    }
+
+    async fn get_channel_users(&self, _channel: &str) -> Result<Vec<String>, Error> {
+        Ok(vec![])
+    }
+}
@@ -1,29 +1,37 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::task::JoinHandle;
 use tracing::{info, error, warn, debug};
 use tokio::sync::Mutex as AsyncMutex;
 use std::sync::Mutex;
 use twilight_cache_inmemory::InMemoryCache;
 use chrono::Utc;
-use sqlx::{Pool, Postgres};
 use maowbot_common::models::discord::DiscordEmbed;
 use maowbot_common::models::platform::{Platform, PlatformCredential};
 use maowbot_common::traits::platform_traits::{ChatPlatform, ConnectionStatus, PlatformIntegration};
-use maowbot_common::traits::repository_traits::CredentialsRepository;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, DiscordRepository, ObsRepository};
 use crate::eventbus::EventBus;
 use crate::services::message_service::MessageService;
+use crate::services::outbound_chain::{OutboundChain, OutboundTarget};
+use crate::services::ai_safety::AiResponseShaper;
+use crate::services::chat_identity::ChatIdentityRouter;
+use crate::services::outbound_guard::OutboundGuard;
 use crate::services::user_service::UserService;
-use crate::{Error, crypto::Encryptor};
+use crate::Error;
 
 use crate::platforms::discord::runtime::DiscordPlatform;
 use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::platforms::twitch::routing::{TwitchAccountRouter, TwitchOperation};
 use crate::platforms::twitch::runtime::TwitchPlatform;
 use crate::platforms::vrchat_pipeline::runtime::VRChatPlatform;
 use crate::platforms::twitch_irc::runtime::TwitchIrcPlatform;
 use crate::platforms::twitch_eventsub::runtime::TwitchEventSubPlatform;
+use crate::platforms::twitch_eventsub::subscriptions::EventSubManager;
 use crate::platforms::obs::ObsRuntime;
-use crate::repositories::postgres::discord::PostgresDiscordRepository;
+use crate::platforms::kick::KickPlatform;
+use crate::platforms::registry::{PlatformFactory, PlatformRegistry, SpawnRequest};
+pub use crate::platforms::supervisor::{BackoffPolicy, ConnectionSnapshot, ConnectionState, ConnectionSupervisor};
 
 pub struct PlatformRuntimeHandle {
     pub join_handle: JoinHandle<()>,
@@ -35,6 +43,70 @@ pub struct PlatformRuntimeHandle {
     pub vrchat_instance: Option<Arc<AsyncMutex<VRChatPlatform>>>,
     pub discord_instance: Option<Arc<DiscordPlatform>>,
     pub obs_instance: Option<Arc<ObsRuntime>>,
+    pub kick_instance: Option<Arc<AsyncMutex<KickPlatform>>>,
+}
+
+impl PlatformRuntimeHandle {
+    /// A handle with no built-in platform instance, for registered platforms.
+    pub fn new(platform: &str, user_id: uuid::Uuid, join_handle: JoinHandle<()>) -> Self {
+        Self {
+            join_handle,
+            platform: platform.to_string(),
+            user_id: user_id.to_string(),
+            started_at: Utc::now(),
+            twitch_irc_instance: None,
+            vrchat_instance: None,
+            discord_instance: None,
+            obs_instance: None,
+            kick_instance: None,
+        }
+    }
+}
+
+/// Starts the platforms that ship with the bot, through `PlatformManager`'s own spawners.
+struct BuiltinPlatformFactory {
+    platform: Platform,
+    name: String,
+}
+
+impl BuiltinPlatformFactory {
+    fn new(platform: Platform) -> Self {
+        Self { name: platform.to_string(), platform }
+    }
+}
+
+#[async_trait::async_trait]
+impl PlatformFactory for BuiltinPlatformFactory {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn credential_platform(&self) -> Option<Platform> {
+        // OBS connects by instance number, not with account credentials
+        (self.platform != Platform::OBS).then(|| self.platform.clone())
+    }
+
+    async fn spawn(&self, manager: &PlatformManager, request: SpawnRequest) -> Result<PlatformRuntimeHandle, Error> {
+        let credential = || request.credential.clone().ok_or_else(|| {
+            Error::Auth(format!("No credentials for user='{}' and platform='{}'", request.account_name, self.name))
+        });
+        match self.platform {
+            Platform::Discord => manager.spawn_discord(credential()?).await,
+            Platform::Twitch => manager.spawn_twitch_helix(credential()?).await,
+            Platform::VRChat => manager.spawn_vrchat(credential()?).await,
+            Platform::TwitchIRC => manager.spawn_twitch_irc(credential()?).await,
+            Platform::TwitchEventSub => manager.spawn_twitch_eventsub(credential()?).await,
+            Platform::Kick => manager.spawn_kick(credential()?).await,
+            Platform::OBS => {
+                // Extract instance number from account_name (e.g., "obs-1" -> 1)
+                let instance_num = request.account_name
+                    .strip_prefix("obs-")
+                    .and_then(|s| s.parse::<u32>().ok())
+                    .ok_or_else(|| Error::Platform(format!("Invalid OBS instance: {}", request.account_name)))?;
+                manager.spawn_obs(instance_num, request.user_id).await
+            }
+        }
+    }
 }
 
 /// Manages starting/stopping platform runtimes, holding references to them, etc.
@@ -43,15 +115,39 @@ pub struct PlatformManager {
     user_svc: Arc<UserService>,
     event_bus: Arc<EventBus>,
     pub(crate) credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
-    encryptor: Encryptor,
-    pool: Pool<Postgres>,
+    obs_repo: Arc<dyn ObsRepository>,
 
     pub active_runtimes: AsyncMutex<HashMap<(String, String), PlatformRuntimeHandle>>,
     pub discord_caches: AsyncMutex<HashMap<(String, String), Arc<InMemoryCache>>>,
-    pub discord_repo: Arc<PostgresDiscordRepository>,
+    pub discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
+
+    /// Tracks connection state and reconnect backoff for supervised runtimes
+    pub connection_supervisor: Arc<ConnectionSupervisor>,
+
+    /// The EventSub websocket session's subscriptions, for listing and repair
+    pub eventsub_subscriptions: Arc<EventSubManager>,
     
     // Reference to the plugin manager - will be set later
     plugin_manager: Mutex<Option<Arc<crate::plugins::manager::PluginManager>>>,
+
+    /// Dedupe and rate checks for outbound chat; set once settings are loaded
+    outbound_guard: Mutex<Option<Arc<OutboundGuard>>>,
+
+    /// Middleware outbound chat runs through before the guard; set once settings are loaded
+    outbound_chain: Mutex<Option<Arc<OutboundChain>>>,
+
+    /// Picks the bot or broadcaster account per Twitch operation; set once settings are loaded
+    twitch_accounts: Mutex<Option<Arc<TwitchAccountRouter>>>,
+
+    /// Which account speaks in which channel; set once settings are loaded
+    chat_identities: Mutex<Option<Arc<ChatIdentityRouter>>>,
+
+    /// Safety filter and chunking for AI answers; set once settings are loaded
+    ai_shaper: Mutex<Option<Arc<AiResponseShaper>>>,
+
+    /// Platforms `start_platform_runtime` can start: the built-ins plus any
+    /// registered by other crates or plugins.
+    pub platforms: Arc<PlatformRegistry>,
 }
 
 impl PlatformManager {
@@ -59,23 +155,55 @@ impl PlatformManager {
         user_svc: Arc<UserService>,
         event_bus: Arc<EventBus>,
         credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
-        discord_repo: Arc<PostgresDiscordRepository>,
-        encryptor: Encryptor,
-        pool: Pool<Postgres>,
+        discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
+        obs_repo: Arc<dyn ObsRepository>,
     ) -> Self {
+        let connection_supervisor = Arc::new(ConnectionSupervisor::new(
+            event_bus.clone(),
+            BackoffPolicy::default(),
+        ));
+        let platforms = Arc::new(PlatformRegistry::new());
+        for platform in [
+            Platform::Twitch,
+            Platform::TwitchIRC,
+            Platform::TwitchEventSub,
+            Platform::Discord,
+            Platform::VRChat,
+            Platform::OBS,
+            Platform::Kick,
+        ] {
+            platforms
+                .register(Arc::new(BuiltinPlatformFactory::new(platform)))
+                .expect("built-in platform names are unique");
+        }
         Self {
             message_service: Mutex::new(None),
             user_svc,
             event_bus,
             credentials_repo,
-            encryptor,
-            pool,
+            obs_repo,
             active_runtimes: AsyncMutex::new(HashMap::new()),
             discord_caches: AsyncMutex::new(HashMap::new()),
             discord_repo,
+            connection_supervisor,
+            eventsub_subscriptions: Arc::new(EventSubManager::new()),
             plugin_manager: Mutex::new(None),
+            outbound_guard: Mutex::new(None),
+            outbound_chain: Mutex::new(None),
+            twitch_accounts: Mutex::new(None),
+            chat_identities: Mutex::new(None),
+            ai_shaper: Mutex::new(None),
+            platforms,
         }
     }
+
+    /// Makes a platform startable by name. Fails if the name is taken.
+    pub fn register_platform(&self, factory: Arc<dyn PlatformFactory>) -> Result<(), Error> {
+        let name = factory.name().to_string();
+        self.platforms.register(factory)?;
+        info!("Registered platform '{}'", name);
+        Ok(())
+    }
     
     /// Set the plugin manager reference
     pub fn set_plugin_manager(&self, plugin_manager: Arc<crate::plugins::manager::PluginManager>) {
@@ -89,6 +217,67 @@ impl PlatformManager {
         pm.clone()
     }
     
+    pub fn set_outbound_guard(&self, guard: Arc<OutboundGuard>) {
+        *self.outbound_guard.lock().unwrap() = Some(guard);
+    }
+
+    pub fn outbound_guard(&self) -> Option<Arc<OutboundGuard>> {
+        self.outbound_guard.lock().unwrap().clone()
+    }
+
+    pub fn set_outbound_chain(&self, chain: Arc<OutboundChain>) {
+        *self.outbound_chain.lock().unwrap() = Some(chain);
+    }
+
+    pub fn outbound_chain(&self) -> Option<Arc<OutboundChain>> {
+        self.outbound_chain.lock().unwrap().clone()
+    }
+
+    pub fn set_twitch_accounts(&self, router: Arc<TwitchAccountRouter>) {
+        *self.twitch_accounts.lock().unwrap() = Some(router);
+    }
+
+    /// The account router for Twitch operations (bot vs broadcaster).
+    pub fn twitch_accounts(&self) -> Result<Arc<TwitchAccountRouter>, Error> {
+        self.twitch_accounts.lock().unwrap().clone()
+            .ok_or_else(|| Error::Internal("Twitch account routing is not set up yet".into()))
+    }
+
+    pub fn set_chat_identities(&self, router: Arc<ChatIdentityRouter>) {
+        *self.chat_identities.lock().unwrap() = Some(router);
+    }
+
+    /// Per-channel account assignments, once set up.
+    pub fn chat_identities(&self) -> Option<Arc<ChatIdentityRouter>> {
+        self.chat_identities.lock().unwrap().clone()
+    }
+
+    pub fn set_ai_shaper(&self, shaper: Arc<AiResponseShaper>) {
+        *self.ai_shaper.lock().unwrap() = Some(shaper);
+    }
+
+    pub fn ai_shaper(&self) -> Option<Arc<AiResponseShaper>> {
+        self.ai_shaper.lock().unwrap().clone()
+    }
+
+    /// False when the outbound guard drops `text` (a repeat, or the channel's
+    /// outbound rate is used up).
+    fn outbound_allowed(&self, platform: &str, channel: &str, text: &str) -> bool {
+        self.outbound_guard().is_none_or(|guard| guard.allow(platform, channel, text))
+    }
+
+    /// The lines to actually send for `text`: the platform's middleware chain
+    /// runs first, then the outbound guard drops repeats and over-rate lines.
+    async fn prepare_outbound(&self, platform: &str, account: &str, channel: &str, text: &str) -> Vec<String> {
+        let lines = match self.outbound_chain() {
+            Some(chain) => chain.process(&OutboundTarget { platform, account, channel }, text).await,
+            None => vec![text.to_string()],
+        };
+        lines.into_iter()
+            .filter(|line| self.outbound_allowed(platform, channel, line))
+            .collect()
+    }
+    
     /// Get access to the AI API through the plugin manager
     pub fn get_ai_api(&self) -> Option<Arc<dyn maowbot_common::traits::api::AiApi + Send + Sync>> {
         // First attempt: try to get from plugin_manager if it exists
@@ -128,41 +317,24 @@ impl PlatformManager {
             .find_user_by_global_username(account_name)
             .await?;
 
-        let platform = platform_str.parse::<Platform>()
-            .map_err(|_| Error::Platform(format!("Unknown platform '{platform_str}'")))?;
-
-        // OBS doesn't use traditional credentials
-        let creds = if platform == Platform::OBS {
-            // Create a dummy credential for OBS
-            maowbot_common::models::platform::PlatformCredential {
-                credential_id: uuid::Uuid::new_v4(),
-                platform: Platform::OBS,
-                platform_id: None,
-                credential_type: maowbot_common::models::credential::CredentialType::APIKey,
-                user_id: user.user_id,
-                user_name: account_name.to_string(),
-                primary_token: format!("obs-instance-{}", account_name),
-                refresh_token: None,
-                additional_data: None,
-                expires_at: None,
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-                is_broadcaster: false,
-                is_teammate: false,
-                is_bot: false,
-            }
-        } else {
-            let creds_opt = self.credentials_repo
-                .get_credentials(&platform, user.user_id)
-                .await?;
-            match creds_opt {
-                Some(c) => c,
-                None => {
-                    return Err(Error::Auth(format!(
-                        "No credentials for user='{account_name}' and platform='{platform_str}'",
-                    )));
+        let factory = self.platforms.get(platform_str)
+            .ok_or_else(|| Error::Platform(format!("Unknown platform '{platform_str}'")))?;
+
+        let credential = match factory.credential_platform() {
+            Some(platform) => {
+                let creds_opt = self.credentials_repo
+                    .get_credentials(&platform, user.user_id)
+                    .await?;
+                match creds_opt {
+                    Some(c) => Some(c),
+                    None => {
+                        return Err(Error::Auth(format!(
+                            "No credentials for user='{account_name}' and platform='{platform_str}'",
+                        )));
+                    }
                 }
             }
+            None => None,
         };
 
         let key = (platform_str.to_string(), user.user_id.to_string());
@@ -174,28 +346,24 @@ impl PlatformManager {
             }
         }
 
-        let handle = match platform {
-            Platform::Discord => self.spawn_discord(creds).await?,
-            Platform::Twitch => self.spawn_twitch_helix(creds).await?,
-            Platform::VRChat => self.spawn_vrchat(creds).await?,
-            Platform::TwitchIRC => self.spawn_twitch_irc(creds).await?,
-            Platform::TwitchEventSub => self.spawn_twitch_eventsub(creds).await?,
-            Platform::OBS => {
-                // OBS uses instance numbers, not user credentials
-                // Extract instance number from account_name (e.g., "obs-1" -> 1)
-                let instance_num = account_name
-                    .strip_prefix("obs-")
-                    .and_then(|s| s.parse::<u32>().ok())
-                    .ok_or_else(|| Error::Platform(format!("Invalid OBS instance: {}", account_name)))?;
-                self.spawn_obs(instance_num, user.user_id).await?
-            }
-        };
+        let handle = factory.spawn(self, SpawnRequest {
+            platform: platform_str.to_string(),
+            account_name: account_name.to_string(),
+            user_id: user.user_id,
+            credential,
+        }).await?;
 
         {
             let mut guard = self.active_runtimes.lock().await;
             guard.insert(key, handle);
         }
 
+        if ConnectionSupervisor::supervises(platform_str) {
+            self.connection_supervisor
+                .mark_connected(platform_str, &user.user_id.to_string(), account_name)
+                .await;
+        }
+
         Ok(())
     }
 
@@ -213,15 +381,101 @@ impl PlatformManager {
             let mut guard = self.active_runtimes.lock().await;
             guard.remove(&key)
         };
+        let was_supervised = self.connection_supervisor
+            .snapshot(platform_str, &user.user_id.to_string())
+            .is_some();
+        self.connection_supervisor
+            .mark_stopped(platform_str, &user.user_id.to_string())
+            .await;
+
         if let Some(rh) = handle_opt {
             rh.join_handle.abort();
             info!("Stopped runtime for platform='{platform_str}', user_id={}", user.user_id);
+        } else if was_supervised {
+            info!("Cancelled pending reconnect for platform='{platform_str}', account='{account_name}'");
         } else {
             warn!("No active runtime for platform='{platform_str}', account='{account_name}'");
         }
         Ok(())
     }
 
+    /// Spawns the connection supervisor loop. Every `interval` it reaps supervised
+    /// runtimes whose task has ended, restarts them once their backoff has elapsed,
+    /// and promotes reconnected runtimes back to `Connected` once they are stable.
+    /// Holds only a weak reference, so it stops when the manager is dropped.
+    pub fn spawn_connection_supervisor(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let weak = Arc::downgrade(self);
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        let Some(manager) = weak.upgrade() else { break };
+                        manager.supervise_runtimes().await;
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            info!("Connection supervisor: shutting down cleanly.");
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// One supervisor pass; see `spawn_connection_supervisor`.
+    pub async fn supervise_runtimes(&self) {
+        // 1) Reap supervised runtimes whose task ended on its own
+        let ended: Vec<((String, String), PlatformRuntimeHandle)> = {
+            let mut guard = self.active_runtimes.lock().await;
+            let keys: Vec<(String, String)> = guard.iter()
+                .filter(|((platform, _), handle)| {
+                    ConnectionSupervisor::supervises(platform) && handle.join_handle.is_finished()
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            keys.into_iter()
+                .filter_map(|key| guard.remove(&key).map(|h| (key, h)))
+                .collect()
+        };
+
+        for ((platform, user_id), handle) in ended {
+            let reason = match handle.join_handle.await {
+                Err(e) if e.is_panic() => "runtime task panicked".to_string(),
+                _ => "connection closed".to_string(),
+            };
+            if platform == "discord" {
+                self.discord_caches.lock().await.remove(&(platform.clone(), user_id.clone()));
+            }
+            self.connection_supervisor.mark_lost(&platform, &user_id, &reason).await;
+        }
+
+        // 2) Retry runtimes whose backoff has elapsed
+        let now = Utc::now();
+        for snapshot in self.connection_supervisor.due_retries(now) {
+            let key = (snapshot.platform.clone(), snapshot.user_id.clone());
+            if self.active_runtimes.lock().await.contains_key(&key) {
+                // Started manually while we were waiting
+                continue;
+            }
+            debug!(
+                "[Supervisor] reconnecting {} for '{}' (attempt {})",
+                snapshot.platform, snapshot.account_name, snapshot.attempt
+            );
+            if let Err(e) = self.start_platform_runtime(&snapshot.platform, &snapshot.account_name).await {
+                self.connection_supervisor
+                    .mark_lost(&snapshot.platform, &snapshot.user_id, &e.to_string())
+                    .await;
+            }
+        }
+
+        // 3) Runtimes that stayed up long enough are healthy again
+        self.connection_supervisor.promote_stable(now).await;
+    }
+
     pub async fn get_discord_platform(
         &self,
         account_name: &str
@@ -295,32 +549,50 @@ impl PlatformManager {
         let join_handle = tokio::spawn({
             let cloned_discord2 = cloned_discord.clone();
             async move {
-                loop {
-                    match cloned_discord2.next_message_event().await {
-                        Some(msg_event) => {
-                            // Include guild_id in metadata if available
-                            let metadata: Vec<String> = if let Some(guild_id) = &msg_event.guild_id {
-                                vec![format!("guild_id:{}", guild_id)]
-                            } else {
-                                Vec::new()
-                            };
-                            
-                            if let Err(e) = msg_svc
-                                .process_incoming_message(
-                                    "discord",
-                                    &msg_event.channel,
-                                    &msg_event.user_id,
-                                    Some(&msg_event.username),
-                                    &msg_event.user_roles,
-                                    &msg_event.text,
-                                    &metadata
-                                )
-                                .await
-                            {
-                                tracing::error!("Discord message error: {e}");
+                while let Some(msg_event) = cloned_discord2.next_message_event().await {
+                    if let Some(slash) = &msg_event.slash_command {
+                        let replies = match msg_svc
+                            .process_slash_command(
+                                "discord",
+                                &msg_event.channel,
+                                &msg_event.user_id,
+                                Some(&msg_event.username),
+                                &msg_event.user_roles,
+                                &slash.name,
+                                &slash.options,
+                            )
+                            .await
+                        {
+                            Ok(replies) => replies,
+                            Err(e) => {
+                                tracing::error!("Discord slash command /{} error: {e}", slash.name);
+                                vec![format!("Something went wrong: {e}")]
                             }
+                        };
+                        if let Err(e) = cloned_discord2
+                            .finish_interaction(&slash.interaction_token, &replies.join("\n"))
+                            .await
+                        {
+                            tracing::error!("{e}");
                         }
-                        None => break,
+                        continue;
+                    }
+
+                    let metadata = msg_event.metadata();
+
+                    if let Err(e) = msg_svc
+                        .process_incoming_message(
+                            "discord",
+                            &msg_event.channel,
+                            &msg_event.user_id,
+                            Some(&msg_event.username),
+                            &msg_event.user_roles,
+                            &msg_event.text,
+                            &metadata
+                        )
+                        .await
+                    {
+                        tracing::error!("Discord message error: {e}");
                     }
                 }
             }
@@ -342,6 +614,7 @@ impl PlatformManager {
             twitch_irc_instance: None,
             vrchat_instance: None,
             obs_instance: None,
+            kick_instance: None,
         })
     }
 
@@ -417,6 +690,7 @@ impl PlatformManager {
             vrchat_instance: None,
             discord_instance: None,
             obs_instance: None,
+            kick_instance: None,
         })
     }
 
@@ -474,6 +748,7 @@ impl PlatformManager {
             vrchat_instance: Some(arc_vrc),
             discord_instance: None,
             obs_instance: None,
+            kick_instance: None,
         })
     }
 
@@ -488,7 +763,7 @@ impl PlatformManager {
         irc.set_event_bus(self.event_bus.clone());
 
         // If this credential is a bot, we can choose whether to skip reading or not:
-        if credential.is_bot == true {
+        if credential.is_bot {
             // For a bot account, we typically want to read commands too, so let's leave
             // enable_incoming = true if we do want them to handle them.
             // However, the example might set false. For now we keep it true.
@@ -512,6 +787,24 @@ impl PlatformManager {
                     let display_name = evt.display_name.clone();
                     let roles = evt.roles.clone();
                     let text = evt.text;
+                    let reply = evt.reply_parent.clone().unwrap_or_default();
+                    let metadata: Vec<String> = [
+                        ("message_id", &evt.message_id),
+                        ("emotes", &evt.emotes),
+                        ("room_id", &evt.room_id),
+                        ("badges", &evt.badges),
+                        ("color", &evt.color),
+                        ("msg_id", &evt.msg_id),
+                        ("reply_parent_msg_id", &reply.message_id),
+                        ("reply_parent_user_id", &reply.user_id),
+                        ("reply_parent_user_login", &reply.user_login),
+                        ("reply_parent_display_name", &reply.display_name),
+                        ("reply_parent_msg_body", &reply.text),
+                    ]
+                        .into_iter()
+                        .filter(|(_, value)| !value.is_empty())
+                        .map(|(key, value)| format!("{}:{}", key, value))
+                        .collect();
 
                     if let Err(e) = message_svc
                         .process_incoming_message(
@@ -521,7 +814,7 @@ impl PlatformManager {
                             Some(&display_name),
                             &roles,
                             &text,
-                            &[],
+                            &metadata,
                         )
                         .await
                     {
@@ -543,6 +836,7 @@ impl PlatformManager {
             vrchat_instance: None,
             discord_instance: None,
             obs_instance: None,
+            kick_instance: None,
         })
     }
 
@@ -557,6 +851,7 @@ impl PlatformManager {
         eventsub.credentials = Some(credential);
 
         eventsub.set_event_bus(event_bus);
+        eventsub.set_subscription_manager(self.eventsub_subscriptions.clone());
 
         let join_handle = tokio::spawn(async move {
             match eventsub.start_loop().await {
@@ -579,6 +874,7 @@ impl PlatformManager {
             vrchat_instance: None,
             discord_instance: None,
             obs_instance: None,
+            kick_instance: None,
         })
     }
     
@@ -586,9 +882,8 @@ impl PlatformManager {
         let mut obs_runtime = ObsRuntime::new(
             instance_number,
             user_id,
-            self.pool.clone(),
+            self.obs_repo.clone(),
             self.event_bus.clone(),
-            self.encryptor.clone(),
         ).await?;
         
         // Connect before creating the Arc
@@ -611,6 +906,66 @@ impl PlatformManager {
             vrchat_instance: None,
             discord_instance: None,
             obs_instance: Some(obs_arc),
+            kick_instance: None,
+        })
+    }
+
+    async fn spawn_kick(&self, credential: PlatformCredential) -> Result<PlatformRuntimeHandle, Error> {
+        let message_svc = self.get_message_service()?;
+        let user_id_str = credential.user_id.to_string();
+        let user_id_str_for_closure = user_id_str.clone();
+
+        let mut kick = KickPlatform::new();
+        kick.set_credentials(credential.clone());
+        kick.set_event_bus(self.event_bus.clone());
+        kick.connect().await?;
+        info!("[Kick] connected for user_id={}", user_id_str);
+
+        // Like Twitch-IRC: every Kick account also reads the other Kick accounts' chats
+        let all_kick_creds = self.credentials_repo
+            .list_credentials_for_platform(&Platform::Kick)
+            .await?;
+        for c in all_kick_creds.iter().filter(|c| c.user_id != credential.user_id) {
+            if let Err(e) = kick.join_channel(&c.user_name).await {
+                warn!("[Kick] join_channel('{}') error => {:?}", c.user_name, e);
+            }
+        }
+
+        let rx_opt = kick.rx.take();
+        let arc_kick = Arc::new(AsyncMutex::new(kick));
+
+        let join_handle = tokio::spawn(async move {
+            if let Some(mut msg_rx) = rx_opt {
+                while let Some(evt) = msg_rx.recv().await {
+                    if let Err(e) = message_svc
+                        .process_incoming_message(
+                            "kick",
+                            &evt.channel,
+                            &evt.kick_user_id,
+                            Some(&evt.display_name),
+                            &evt.roles,
+                            &evt.text,
+                            &[],
+                        )
+                        .await
+                    {
+                        error!("[Kick] process_incoming_message => {e:?}");
+                    }
+                }
+            }
+            info!("[Kick] read loop ended for user_id={}", user_id_str_for_closure);
+        });
+
+        Ok(PlatformRuntimeHandle {
+            join_handle,
+            platform: "kick".into(),
+            user_id: user_id_str,
+            started_at: Utc::now(),
+            twitch_irc_instance: None,
+            vrchat_instance: None,
+            discord_instance: None,
+            obs_instance: None,
+            kick_instance: Some(arc_kick),
         })
     }
 
@@ -681,6 +1036,31 @@ impl PlatformManager {
     }
 
     pub async fn send_twitch_irc_message(&self, account_name: &str, channel: &str, text: &str) -> Result<(), Error> {
+        self.send_twitch_irc(account_name, channel, text, None).await
+    }
+
+    /// Sends `text` as a reply to the chat message with id `parent_id`.
+    pub async fn send_twitch_irc_reply(
+        &self,
+        account_name: &str,
+        channel: &str,
+        parent_id: &str,
+        text: &str,
+    ) -> Result<(), Error> {
+        self.send_twitch_irc(account_name, channel, text, Some(parent_id)).await
+    }
+
+    async fn send_twitch_irc(
+        &self,
+        account_name: &str,
+        channel: &str,
+        text: &str,
+        parent_id: Option<&str>,
+    ) -> Result<(), Error> {
+        let lines = self.prepare_outbound("twitch-irc", account_name, channel, text).await;
+        if lines.is_empty() {
+            return Ok(());
+        }
         let user = self.user_svc.find_user_by_global_username(account_name).await?;
         let key = ("twitch-irc".to_string(), user.user_id.to_string());
 
@@ -689,7 +1069,13 @@ impl PlatformManager {
         if let Some(handle) = handle_opt {
             if let Some(irc_arc) = &handle.twitch_irc_instance {
                 let irc_lock = irc_arc.lock().await;
-                irc_lock.send_message(channel, text).await?;
+                for (i, line) in lines.iter().enumerate() {
+                    // Only the first line of a split message is threaded as the reply
+                    match parent_id.filter(|_| i == 0) {
+                        Some(parent_id) => irc_lock.send_reply(channel, parent_id, line).await?,
+                        None => irc_lock.send_message(channel, line).await?,
+                    }
+                }
                 Ok(())
             } else {
                 Err(Error::Platform(format!(
@@ -706,44 +1092,27 @@ impl PlatformManager {
     pub async fn timeout_twitch_user(
         &self,
         _account_name: &str,                 // kept for API parity – no longer used
-        channel:       &str,                 // e.g. "#kittyn"
+        _channel:      &str,                 // e.g. "#kittyn"; always the broadcaster's
         target_user:   &str,                 // login name
         seconds:       u32,                  // 0 = perm‑ban, else timeout
         reason:        Option<&str>,
     ) -> Result<(), Error> {
-        // --- 1. Grab broadcaster credential (must include `moderator:manage:banned_users`). ---
-        let cred = self.credentials_repo
-            .get_broadcaster_credential(&maowbot_common::models::platform::Platform::Twitch)
-            .await?
-            .ok_or_else(|| Error::Platform("No broadcaster Twitch credential found".into()))?;
-
-        let client_id = cred
-            .additional_data
-            .as_ref()
-            .and_then(|d| d.get("client_id").and_then(|v| v.as_str()))
-            .ok_or_else(|| Error::Platform("Broadcaster credential missing client_id".into()))?;
-
-        let broadcaster_id = cred
-            .platform_id
-            .clone()
-            .ok_or_else(|| Error::Platform("Broadcaster credential missing platform_id".into()))?;
-
-        let helix = crate::platforms::twitch::client::TwitchHelixClient::new(
-            &cred.primary_token,
-            client_id,
-        );
+        // --- 1. The moderation account (bot by default, see twitch.account_routing);
+        //        its token needs `moderator:manage:banned_users`. ---
+        let helix = self.twitch_accounts()?.helix_for(TwitchOperation::Moderation).await?;
+        let broadcaster_id = helix.channel_id()?;
 
         // --- 2. Resolve user‑id of the target login. ---
-        let user_id = helix
+        let user_id = helix.client
             .fetch_user_id(target_user)
             .await?
             .ok_or_else(|| Error::Platform(format!("Unknown Twitch login: {target_user}")))?;
 
-        // --- 3. Issue the ban / timeout. Moderator = broadcaster for simplicity. ---
-        helix
+        // --- 3. Issue the ban / timeout as the routed account. ---
+        helix.client
             .ban_user(
-                &broadcaster_id,
-                &broadcaster_id,                 // moderator = broadcaster
+                broadcaster_id,
+                &helix.acting_id,
                 &user_id,
                 if seconds == 0 { None } else { Some(seconds) },
                 reason,
@@ -786,6 +1155,38 @@ impl PlatformManager {
         Ok(())
     }
 
+    // -------------------------------------------------------------
+    // Kick helpers
+    // -------------------------------------------------------------
+    /// Sends `text` to a Kick channel ("#slug" or "slug") as `account_name`.
+    /// The stored credential is re-read first so a token renewed by the
+    /// refresh task is picked up without restarting the runtime.
+    pub async fn send_kick_message(&self, account_name: &str, channel: &str, text: &str) -> Result<(), Error> {
+        let lines = self.prepare_outbound("kick", account_name, channel, text).await;
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let user = self.user_svc.find_user_by_global_username(account_name).await?;
+        let key = ("kick".to_string(), user.user_id.to_string());
+
+        let kick_arc = {
+            let guard = self.active_runtimes.lock().await;
+            guard.get(&key)
+                .and_then(|h| h.kick_instance.clone())
+                .ok_or_else(|| Error::Platform(format!(
+                    "No active kick runtime for account='{account_name}'"
+                )))?
+        };
+        let mut kick = kick_arc.lock().await;
+        if let Some(cred) = self.credentials_repo.get_credentials(&Platform::Kick, user.user_id).await? {
+            kick.set_credentials(cred);
+        }
+        for line in &lines {
+            kick.send_message(channel, line).await?;
+        }
+        Ok(())
+    }
+
     // -------------------------------------------------------------
     // OBS helpers
     // -------------------------------------------------------------
@@ -893,6 +1294,33 @@ impl PlatformManager {
         channel_id_or_name: &str,
         text: &str
     ) -> Result<(), Error> {
+        self.send_discord(account_name, server_id, channel_id_or_name, text, None).await
+    }
+
+    /// Sends `text` as a reply to the Discord message with id `reply_to`.
+    pub async fn send_discord_reply(
+        &self,
+        account_name: &str,
+        server_id: &str,
+        channel_id_or_name: &str,
+        reply_to: &str,
+        text: &str
+    ) -> Result<(), Error> {
+        self.send_discord(account_name, server_id, channel_id_or_name, text, Some(reply_to)).await
+    }
+
+    async fn send_discord(
+        &self,
+        account_name: &str,
+        server_id: &str,
+        channel_id_or_name: &str,
+        text: &str,
+        reply_to: Option<&str>,
+    ) -> Result<(), Error> {
+        let lines = self.prepare_outbound("discord", account_name, channel_id_or_name, text).await;
+        if lines.is_empty() {
+            return Ok(());
+        }
         let user = self.user_svc.find_user_by_global_username(account_name).await?;
         let key = ("discord".to_string(), user.user_id.to_string());
         
@@ -916,7 +1344,13 @@ impl PlatformManager {
         if let Some(handle) = guard.get(&key) {
             if let Some(discord_arc) = &handle.discord_instance {
                 let discord_lock = discord_arc;
-                discord_lock.send_message(&channel_id, text).await
+                for (i, line) in lines.iter().enumerate() {
+                    match reply_to.filter(|_| i == 0) {
+                        Some(reply_to) => discord_lock.send_reply(&channel_id, reply_to, line).await?,
+                        None => discord_lock.send_message(&channel_id, line).await?,
+                    }
+                }
+                Ok(())
             } else {
                 Err(Error::Platform(format!(
                     "No DiscordPlatform instance found for account='{account_name}'"
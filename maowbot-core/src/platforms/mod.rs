@@ -4,7 +4,10 @@ pub mod twitch;
 pub mod discord;
 pub mod vrchat;
 pub mod manager;
+pub mod supervisor;
+pub mod registry;
 pub mod twitch_irc;
 pub mod twitch_eventsub;
 pub mod vrchat_pipeline;
-pub mod obs;
\ No newline at end of file
+pub mod obs;
+pub mod kick;
\ No newline at end of file
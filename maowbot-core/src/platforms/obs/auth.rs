@@ -1,21 +1,20 @@
 use crate::Error;
 use async_trait::async_trait;
-use maowbot_common::models::platform::Platform;
 use maowbot_common::traits::platform_traits::PlatformAuth;
-use sqlx::{Pool, Postgres};
-use tracing::info;
+use maowbot_common::traits::repository_traits::ObsRepository;
+use std::sync::Arc;
 
 /// OBS uses simple password authentication, not OAuth
 pub struct ObsAuthenticator {
     instance_number: u32,
-    pool: Pool<Postgres>,
+    repository: Arc<dyn ObsRepository>,
 }
 
 impl ObsAuthenticator {
-    pub fn new(instance_number: u32, pool: Pool<Postgres>) -> Self {
+    pub fn new(instance_number: u32, repository: Arc<dyn ObsRepository>) -> Self {
         Self {
             instance_number,
-            pool,
+            repository,
         }
     }
 }
@@ -40,17 +39,7 @@ impl PlatformAuth for ObsAuthenticator {
 
     async fn is_authenticated(&self) -> Result<bool, Error> {
         // Check if this instance has connection info in the database
-        let result = sqlx::query(
-            r#"
-            SELECT instance_number 
-            FROM obs_instances 
-            WHERE instance_number = $1
-            "#
-        )
-        .bind(self.instance_number as i32)
-        .fetch_optional(&self.pool)
-        .await?;
-        
-        Ok(result.is_some())
+        let instance = self.repository.get_instance(self.instance_number).await?;
+        Ok(instance.is_some())
     }
 }
\ No newline at end of file
@@ -1,16 +1,13 @@
-use crate::{Error, crypto::Encryptor};
+use crate::Error;
 use crate::eventbus::{EventBus, BotEvent};
-use crate::repositories::postgres::obs::PostgresObsRepository;
 use maowbot_common::traits::repository_traits::ObsRepository;
 use async_trait::async_trait;
-use maowbot_common::models::platform::Platform;
 use maowbot_common::traits::platform_traits::{PlatformIntegration, PlatformAuth, ConnectionStatus};
-use maowbot_obs::{ObsClient, ObsInstance};
-use sqlx::{Pool, Postgres};
+use maowbot_obs::ObsClient;
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{sleep, Duration};
-use tracing::{debug, error, info, warn};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 pub struct ObsRuntime {
@@ -19,7 +16,7 @@ pub struct ObsRuntime {
     client: Arc<ObsClient>,
     status: Arc<RwLock<ConnectionStatus>>,
     event_bus: Arc<EventBus>,
-    repository: PostgresObsRepository,
+    repository: Arc<dyn ObsRepository>,
     shutdown_tx: mpsc::Sender<()>,
     shutdown_rx: Arc<RwLock<Option<mpsc::Receiver<()>>>>,
 }
@@ -28,12 +25,9 @@ impl ObsRuntime {
     pub async fn new(
         instance_number: u32,
         user_id: Uuid,
-        pool: Pool<Postgres>,
+        repository: Arc<dyn ObsRepository>,
         event_bus: Arc<EventBus>,
-        encryptor: Encryptor,
     ) -> Result<Self, Error> {
-        let repository = PostgresObsRepository::new(pool, encryptor);
-        
         // Get instance config from database
         let instance = repository.get_instance(instance_number)
             .await?
@@ -91,13 +85,24 @@ impl ObsRuntime {
                     format!("OBS instance {} connected", self.instance_number)
                 )).await;
                 
-                // Wait for disconnect or shutdown
+                // Wait for disconnect or shutdown, watching the program scene meanwhile
+                let mut current_scene = self.current_scene().await;
                 loop {
                     if !self.client.is_connected().await {
                         warn!("OBS instance {} disconnected", self.instance_number);
                         break;
                     }
                     sleep(Duration::from_secs(5)).await;
+
+                    let scene = self.current_scene().await;
+                    if scene.is_some() && scene != current_scene {
+                        self.event_bus.publish(BotEvent::ObsSceneChanged {
+                            instance: self.instance_number,
+                            scene: scene.clone().unwrap_or_default(),
+                            timestamp: chrono::Utc::now(),
+                        }).await;
+                        current_scene = scene;
+                    }
                 }
                 
                 // Emit disconnection event
@@ -116,6 +121,13 @@ impl ObsRuntime {
         }
     }
     
+    async fn current_scene(&self) -> Option<String> {
+        self.client.list_scenes().await.ok()?
+            .into_iter()
+            .find(|s| s.is_current)
+            .map(|s| s.name)
+    }
+
     pub fn get_client(&self) -> Arc<ObsClient> {
         self.client.clone()
     }
@@ -161,7 +173,7 @@ impl PlatformIntegration for ObsRuntime {
         Ok(())
     }
     
-    async fn send_message(&self, channel: &str, message: &str) -> Result<(), Error> {
+    async fn send_message(&self, _channel: &str, _message: &str) -> Result<(), Error> {
         // OBS doesn't send chat messages
         Err(Error::Platform("OBS does not support sending messages".into()))
     }
@@ -0,0 +1,156 @@
+// File: src/platforms/registry.rs
+//
+// Platform runtimes by name. `PlatformManager::start_platform_runtime` looks the
+// platform up here instead of matching on the `Platform` enum, so other crates
+// and in-process plugins can contribute integrations by registering a
+// `PlatformFactory`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use async_trait::async_trait;
+use uuid::Uuid;
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+
+use crate::platforms::manager::{PlatformManager, PlatformRuntimeHandle};
+use crate::Error;
+
+/// What a factory gets when a runtime is started for one account.
+#[derive(Debug, Clone)]
+pub struct SpawnRequest {
+    /// The platform name as the caller gave it (also the runtime's key).
+    pub platform: String,
+    pub account_name: String,
+    pub user_id: Uuid,
+    /// The account's stored credential, when `credential_platform` names one.
+    pub credential: Option<PlatformCredential>,
+}
+
+/// Starts runtimes for one platform.
+#[async_trait]
+pub trait PlatformFactory: Send + Sync {
+    /// Name used by `platform start <name>`; matched case-insensitively.
+    fn name(&self) -> &str;
+
+    /// Whose stored credentials the runtime needs. With `None` the manager
+    /// spawns without one and the factory finds its own secrets.
+    fn credential_platform(&self) -> Option<Platform> {
+        None
+    }
+
+    /// Connects and spawns the runtime's task. The manager keeps the handle and
+    /// aborts its task on `stop_platform_runtime`.
+    async fn spawn(&self, manager: &PlatformManager, request: SpawnRequest) -> Result<PlatformRuntimeHandle, Error>;
+}
+
+struct Registered {
+    factory: Arc<dyn PlatformFactory>,
+    /// The plugin that registered it, so unloading the plugin removes only its own platforms.
+    owner: Option<String>,
+}
+
+/// The factories the platform manager can start, keyed by lowercase name.
+#[derive(Default)]
+pub struct PlatformRegistry {
+    factories: RwLock<HashMap<String, Registered>>,
+}
+
+impl PlatformRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a factory. Fails if its name is already taken, so a plugin can't
+    /// silently replace a built-in platform.
+    pub fn register(&self, factory: Arc<dyn PlatformFactory>) -> Result<(), Error> {
+        self.insert(factory, None)
+    }
+
+    /// Like `register`, remembering that `owner` (a plugin name) contributed it.
+    pub fn register_owned(&self, factory: Arc<dyn PlatformFactory>, owner: &str) -> Result<(), Error> {
+        self.insert(factory, Some(owner.to_string()))
+    }
+
+    fn insert(&self, factory: Arc<dyn PlatformFactory>, owner: Option<String>) -> Result<(), Error> {
+        let name = factory.name().to_lowercase();
+        if name.is_empty() {
+            return Err(Error::Platform("Platform factory has an empty name".into()));
+        }
+        let mut factories = self.factories.write().unwrap();
+        if factories.contains_key(&name) {
+            return Err(Error::Platform(format!("Platform '{}' is already registered", name)));
+        }
+        factories.insert(name, Registered { factory, owner });
+        Ok(())
+    }
+
+    /// Removes a factory; runtimes it already started keep running until stopped.
+    pub fn unregister(&self, name: &str) -> bool {
+        self.factories.write().unwrap().remove(&name.to_lowercase()).is_some()
+    }
+
+    /// Removes every factory `owner` registered and returns their names.
+    pub fn unregister_owner(&self, owner: &str) -> Vec<String> {
+        let mut factories = self.factories.write().unwrap();
+        let names: Vec<String> = factories.iter()
+            .filter(|(_, r)| r.owner.as_deref() == Some(owner))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &names {
+            factories.remove(name);
+        }
+        names
+    }
+
+    /// The factory for `name`. Built-in aliases such as `twitch-helix` resolve
+    /// to their canonical platform.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn PlatformFactory>> {
+        let factories = self.factories.read().unwrap();
+        factories.get(&name.to_lowercase())
+            .or_else(|| {
+                let canonical = name.parse::<Platform>().ok()?.to_string();
+                factories.get(&canonical)
+            })
+            .map(|r| r.factory.clone())
+    }
+
+    /// Registered platform names, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.factories.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Named(&'static str);
+
+    #[async_trait]
+    impl PlatformFactory for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn spawn(&self, _manager: &PlatformManager, _request: SpawnRequest) -> Result<PlatformRuntimeHandle, Error> {
+            Err(Error::Platform("not used".into()))
+        }
+    }
+
+    #[test]
+    fn test_lookup_is_case_insensitive_and_resolves_aliases() {
+        let registry = PlatformRegistry::new();
+        registry.register(Arc::new(Named("twitch"))).unwrap();
+        registry.register_owned(Arc::new(Named("Matrix")), "matrix-plugin").unwrap();
+
+        assert!(registry.get("MATRIX").is_some());
+        assert_eq!(registry.get("twitch-helix").unwrap().name(), "twitch");
+        assert!(registry.get("irc").is_none());
+        assert!(registry.register_owned(Arc::new(Named("twitch")), "other-plugin").is_err());
+
+        assert!(registry.unregister_owner("other-plugin").is_empty());
+        assert_eq!(registry.unregister_owner("matrix-plugin"), vec!["matrix".to_string()]);
+        assert_eq!(registry.names(), vec!["twitch".to_string()]);
+    }
+}
@@ -0,0 +1,290 @@
+// File: maowbot-core/src/platforms/supervisor.rs
+//
+// Connection bookkeeping for platform runtimes. The PlatformManager drives the
+// reconnect loop; this module decides how long to wait, when to give up, and
+// publishes every state change on the event bus.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rand::Rng;
+use tracing::{info, warn};
+
+use crate::eventbus::{BotEvent, EventBus};
+
+/// Runtimes with a long-lived connection that the supervisor restarts when it drops.
+/// Helix is a request/response stub and OBS runs its own reconnect loop.
+pub const SUPERVISED_PLATFORMS: &[&str] = &["discord", "twitch-irc", "twitch-eventsub", "vrchat", "kick"];
+
+/// Exponential backoff with jitter and a retry budget.
+#[derive(Debug, Clone)]
+pub struct BackoffPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    /// Fraction of each delay randomised in either direction (0.0 - 1.0)
+    pub jitter: f64,
+    /// Reconnect attempts before giving up; `None` retries forever
+    pub max_retries: Option<u32>,
+    /// Uptime after which a reconnected runtime counts as healthy and its budget resets
+    pub stable_after: Duration,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(300),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_retries: Some(10),
+            stable_after: Duration::from_secs(120),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before reconnect `attempt` (1-based), without jitter.
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(32) as i32;
+        let secs = self.initial_delay.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        Duration::from_secs_f64(secs.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// Delay before reconnect `attempt` with jitter applied, never above `max_delay`.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let base = self.base_delay(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 || base.is_zero() {
+            return base;
+        }
+        let factor = rand::rng().random_range((1.0 - jitter)..=(1.0 + jitter));
+        Duration::from_secs_f64(base.as_secs_f64() * factor).min(self.max_delay)
+    }
+
+    pub fn is_exhausted(&self, attempt: u32) -> bool {
+        matches!(self.max_retries, Some(max) if attempt > max)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Running and stable
+    Connected,
+    /// Running again after a drop, but not yet up for `stable_after`
+    Degraded,
+    /// Dropped; waiting out the backoff before the next attempt
+    Reconnecting,
+    /// Retry budget exhausted; needs a manual start
+    Failed,
+    Stopped,
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionState::Connected => write!(f, "connected"),
+            ConnectionState::Degraded => write!(f, "degraded"),
+            ConnectionState::Reconnecting => write!(f, "reconnecting"),
+            ConnectionState::Failed => write!(f, "failed"),
+            ConnectionState::Stopped => write!(f, "stopped"),
+        }
+    }
+}
+
+/// What the supervisor knows about one (platform, user_id) runtime.
+#[derive(Debug, Clone)]
+pub struct ConnectionSnapshot {
+    pub platform: String,
+    pub user_id: String,
+    pub account_name: String,
+    pub state: ConnectionState,
+    /// Reconnect attempts since the runtime was last stable
+    pub attempt: u32,
+    pub last_error: Option<String>,
+    pub changed_at: DateTime<Utc>,
+    pub connected_since: Option<DateTime<Utc>>,
+    pub next_retry_at: Option<DateTime<Utc>>,
+}
+
+pub struct ConnectionSupervisor {
+    policy: BackoffPolicy,
+    event_bus: Arc<EventBus>,
+    states: DashMap<(String, String), ConnectionSnapshot>,
+}
+
+impl ConnectionSupervisor {
+    pub fn new(event_bus: Arc<EventBus>, policy: BackoffPolicy) -> Self {
+        Self { policy, event_bus, states: DashMap::new() }
+    }
+
+    pub fn policy(&self) -> &BackoffPolicy {
+        &self.policy
+    }
+
+    pub fn supervises(platform: &str) -> bool {
+        SUPERVISED_PLATFORMS.contains(&platform)
+    }
+
+    pub fn snapshot(&self, platform: &str, user_id: &str) -> Option<ConnectionSnapshot> {
+        self.states
+            .get(&(platform.to_string(), user_id.to_string()))
+            .map(|s| s.clone())
+    }
+
+    pub fn snapshots(&self) -> Vec<ConnectionSnapshot> {
+        self.states.iter().map(|s| s.value().clone()).collect()
+    }
+
+    /// Runtimes whose backoff has elapsed and should be started again.
+    pub fn due_retries(&self, now: DateTime<Utc>) -> Vec<ConnectionSnapshot> {
+        self.states
+            .iter()
+            .filter(|s| s.state == ConnectionState::Reconnecting
+                && s.next_retry_at.map(|t| t <= now).unwrap_or(true))
+            .map(|s| s.value().clone())
+            .collect()
+    }
+
+    /// A runtime started. After a drop it stays `Degraded` until it has been up for `stable_after`.
+    pub async fn mark_connected(&self, platform: &str, user_id: &str, account_name: &str) {
+        let now = Utc::now();
+        let key = (platform.to_string(), user_id.to_string());
+        let snapshot = {
+            let mut entry = self.states.entry(key).or_insert_with(|| ConnectionSnapshot {
+                platform: platform.to_string(),
+                user_id: user_id.to_string(),
+                account_name: account_name.to_string(),
+                state: ConnectionState::Stopped,
+                attempt: 0,
+                last_error: None,
+                changed_at: now,
+                connected_since: None,
+                next_retry_at: None,
+            });
+            if matches!(entry.state, ConnectionState::Failed | ConnectionState::Stopped) {
+                entry.attempt = 0;
+                entry.last_error = None;
+            }
+            entry.account_name = account_name.to_string();
+            entry.state = if entry.attempt > 0 { ConnectionState::Degraded } else { ConnectionState::Connected };
+            entry.changed_at = now;
+            entry.connected_since = Some(now);
+            entry.next_retry_at = None;
+            entry.clone()
+        };
+        self.publish(&snapshot).await;
+    }
+
+    /// A runtime dropped or a reconnect failed; schedules the next attempt or gives up.
+    pub async fn mark_lost(&self, platform: &str, user_id: &str, error: &str) -> ConnectionState {
+        let now = Utc::now();
+        let key = (platform.to_string(), user_id.to_string());
+        let snapshot = {
+            let Some(mut entry) = self.states.get_mut(&key) else {
+                return ConnectionState::Stopped;
+            };
+            entry.attempt += 1;
+            entry.last_error = Some(error.to_string());
+            entry.changed_at = now;
+            entry.connected_since = None;
+            if self.policy.is_exhausted(entry.attempt) {
+                entry.state = ConnectionState::Failed;
+                entry.next_retry_at = None;
+                warn!(
+                    "[Supervisor] {} runtime for '{}' gave up after {} attempts: {}",
+                    platform, entry.account_name, entry.attempt - 1, error
+                );
+            } else {
+                let delay = self.policy.delay_for(entry.attempt);
+                entry.state = ConnectionState::Reconnecting;
+                entry.next_retry_at = chrono::Duration::from_std(delay).ok().map(|d| now + d);
+                info!(
+                    "[Supervisor] {} runtime for '{}' lost ({}); retry #{} in {:.1}s",
+                    platform, entry.account_name, error, entry.attempt, delay.as_secs_f64()
+                );
+            }
+            entry.clone()
+        };
+        self.publish(&snapshot).await;
+        snapshot.state
+    }
+
+    /// Manual stop: forget the runtime so no pending retry fires.
+    pub async fn mark_stopped(&self, platform: &str, user_id: &str) {
+        let key = (platform.to_string(), user_id.to_string());
+        if let Some((_, mut snapshot)) = self.states.remove(&key) {
+            snapshot.state = ConnectionState::Stopped;
+            snapshot.changed_at = Utc::now();
+            snapshot.connected_since = None;
+            snapshot.next_retry_at = None;
+            self.publish(&snapshot).await;
+        }
+    }
+
+    /// Promotes `Degraded` runtimes that stayed up for `stable_after` and resets their budget.
+    pub async fn promote_stable(&self, now: DateTime<Utc>) {
+        let stable_after = chrono::Duration::from_std(self.policy.stable_after)
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        let mut promoted = Vec::new();
+        for mut entry in self.states.iter_mut() {
+            let stable = entry.connected_since.map(|t| now - t >= stable_after).unwrap_or(false);
+            if stable && entry.state == ConnectionState::Degraded {
+                entry.state = ConnectionState::Connected;
+                entry.attempt = 0;
+                entry.changed_at = now;
+                promoted.push(entry.clone());
+            } else if stable && entry.attempt > 0 {
+                entry.attempt = 0;
+            }
+        }
+        for snapshot in promoted {
+            self.publish(&snapshot).await;
+        }
+    }
+
+    async fn publish(&self, snapshot: &ConnectionSnapshot) {
+        self.event_bus.publish(BotEvent::PlatformConnectionChanged {
+            platform: snapshot.platform.clone(),
+            account_name: snapshot.account_name.clone(),
+            state: snapshot.state.to_string(),
+            attempt: snapshot.attempt,
+            error: snapshot.last_error.clone(),
+            next_retry_at: snapshot.next_retry_at,
+            timestamp: snapshot.changed_at,
+        }).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = BackoffPolicy {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.0,
+            max_retries: Some(3),
+            stable_after: Duration::from_secs(60),
+        };
+        assert_eq!(policy.delay_for(1), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(3), Duration::from_secs(4));
+        assert_eq!(policy.delay_for(8), Duration::from_secs(10));
+        assert!(!policy.is_exhausted(3));
+        assert!(policy.is_exhausted(4));
+    }
+
+    #[test]
+    fn test_jitter_stays_in_bounds() {
+        let policy = BackoffPolicy { jitter: 0.5, ..BackoffPolicy::default() };
+        for _ in 0..100 {
+            let d = policy.delay_for(3).as_secs_f64();
+            assert!((2.0..=6.0).contains(&d), "delay {d} outside jitter range");
+        }
+    }
+}
@@ -11,6 +11,7 @@ use twitch_oauth2::{
 };
 
 use crate::Error;
+use super::device_code::{self, PendingDeviceCode};
 use maowbot_common::models::auth::{AuthenticationPrompt, AuthenticationResponse};
 use maowbot_common::traits::auth_traits::PlatformAuthenticator;
 use maowbot_common::models::credential::{CredentialType};
@@ -22,7 +23,6 @@ struct TwitchTokenResponse {
     refresh_token: Option<String>,
     expires_in: u64,
     scope: Option<Vec<String>>,
-    token_type: String,
 }
 
 /// For /validate
@@ -31,11 +31,34 @@ struct TwitchValidateResponse {
     client_id: String,
     login: String,
     user_id: String,
-    expires_in: u64,
 }
 
 static STATE_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
+/// Example set of scopes for channel bits, ads, etc. Adjust as needed.
+/// Stored tokens are checked against this list at startup (see `scope_check`).
+pub const HELIX_SCOPES: &[&str] = &[
+    "bits:read",
+    "channel:read:ads",
+    "user:read:chat",
+    "channel:read:subscriptions",
+    "channel:moderate",
+    "moderator:read:unban_requests",
+    "channel:read:hype_train",
+    "moderator:read:shoutouts",
+    "channel:manage:redemptions",
+    "moderator:read:followers",
+    "moderator:read:chatters",
+    "moderator:manage:banned_users",
+    "channel:manage:broadcast",
+    "channel:manage:schedule",
+    "moderator:manage:shield_mode",
+    "moderator:manage:chat_settings",
+    "moderator:manage:chat_messages",
+    "user:manage:whispers",
+    "clips:edit",
+];
+
 pub struct TwitchAuthenticator {
     pub client_id: String,
     pub client_secret: Option<String>,
@@ -43,6 +66,7 @@ pub struct TwitchAuthenticator {
     pub is_teammate: bool,
     pub is_bot: bool,
     pending_state: Option<String>,
+    pending_device: Option<PendingDeviceCode>,
 }
 
 impl TwitchAuthenticator {
@@ -54,26 +78,13 @@ impl TwitchAuthenticator {
             is_teammate: false,
             is_bot: false,
             pending_state: None,
+            pending_device: None,
         }
     }
 
     /// Include all scopes needed for your Helix-based flows **plus** what’s required for EventSub.
     fn build_auth_url(&self, state: &str) -> String {
-        // Example set of scopes for channel bits, ads, etc. Adjust as needed.
-        let scopes = vec![
-            "bits:read",
-            "channel:read:ads",
-            "user:read:chat",
-            "channel:read:subscriptions",
-            "channel:moderate",
-            "moderator:read:unban_requests",
-            "channel:read:hype_train",
-            "moderator:read:shoutouts",
-            "channel:manage:redemptions",
-            "moderator:read:followers",
-            "moderator:manage:banned_users",
-        ];
-        let scope_str = scopes.join(" ");
+        let scope_str = HELIX_SCOPES.join(" ");
         let redirect_uri = "http://localhost:9876/callback";
 
         format!(
@@ -110,6 +121,45 @@ impl TwitchAuthenticator {
         // Return (login, user_id, client_id from validate)
         Ok((validate.login, validate.user_id, validate.client_id))
     }
+
+    /// Builds a new credential from a token response (authorization code or device grant).
+    async fn credential_from_token(&self, resp: TwitchTokenResponse) -> Result<PlatformCredential, Error> {
+        let now = Utc::now();
+        let expires_at = Some(Utc::now() + chrono::Duration::seconds(resp.expires_in as i64));
+
+        // Fetch login, user_id, and validated client_id
+        let (login, external_user_id, validate_cid) =
+            self.fetch_user_login_and_id(&resp.access_token).await?;
+
+        // Build final credential
+        let credential = PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::Twitch,
+            credential_type: CredentialType::OAuth2,
+            user_id: Uuid::new_v4(), // Will be updated later
+            primary_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            additional_data: Some(serde_json::json!({
+                "scope": resp.scope.unwrap_or_default(),
+                // KEY CHANGE: Store client_id so that it matches the token
+                "client_id": self.client_id,
+                // If you prefer to store the validated one:
+                "validate_client_id": validate_cid,
+            })),
+            expires_at,
+            created_at: now,
+            updated_at: now,
+            is_broadcaster: self.is_broadcaster,
+            is_teammate: self.is_teammate,
+            is_bot: self.is_bot,
+
+            // external user/broadcaster
+            platform_id: Some(external_user_id),
+            user_name: login,
+        };
+
+        Ok(credential)
+    }
 }
 
 #[async_trait]
@@ -159,42 +209,25 @@ impl PlatformAuthenticator for TwitchAuthenticator {
             .await
             .map_err(|e| Error::Auth(format!("Parse error on token JSON: {e}")))?;
 
-        let now = Utc::now();
-        let expires_at = Some(Utc::now() + chrono::Duration::seconds(resp.expires_in as i64));
-
-        // Fetch login, user_id, and validated client_id
-        let (login, external_user_id, validate_cid) =
-            self.fetch_user_login_and_id(&resp.access_token).await?;
+        self.pending_state = None;
+        self.credential_from_token(resp).await
+    }
 
-        // Build final credential
-        let credential = PlatformCredential {
-            credential_id: Uuid::new_v4(),
-            platform: Platform::Twitch,
-            credential_type: CredentialType::OAuth2,
-            user_id: Uuid::new_v4(), // Will be updated later
-            primary_token: resp.access_token,
-            refresh_token: resp.refresh_token,
-            additional_data: Some(serde_json::json!({
-                "scope": resp.scope.unwrap_or_default(),
-                // KEY CHANGE: Store client_id so that it matches the token
-                "client_id": self.client_id,
-                // If you prefer to store the validated one:
-                "validate_client_id": validate_cid,
-            })),
-            expires_at,
-            created_at: now,
-            updated_at: now,
-            is_broadcaster: self.is_broadcaster,
-            is_teammate: self.is_teammate,
-            is_bot: self.is_bot,
+    async fn start_device_authentication(&mut self) -> Result<AuthenticationPrompt, Error> {
+        let (prompt, pending) = device_code::request_device_code(&self.client_id, HELIX_SCOPES).await?;
+        self.pending_device = Some(pending);
+        Ok(prompt)
+    }
 
-            // external user/broadcaster
-            platform_id: Some(external_user_id),
-            user_name: login,
+    async fn poll_device_authentication(&mut self) -> Result<Option<PlatformCredential>, Error> {
+        let Some(pending) = self.pending_device.as_mut() else {
+            return Err(Error::Auth("No device-code login in progress".into()));
         };
-
-        self.pending_state = None;
-        Ok(credential)
+        let Some(resp) = device_code::poll_device_token::<TwitchTokenResponse>(&self.client_id, pending).await? else {
+            return Ok(None);
+        };
+        self.pending_device = None;
+        self.credential_from_token(resp).await.map(Some)
     }
 
     async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
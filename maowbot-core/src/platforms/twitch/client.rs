@@ -22,6 +22,9 @@ pub struct ValidateTokenResponse {
     pub login: String,
     pub user_id: String,
     pub expires_in: u64,
+    /// What the token was actually granted
+    #[serde(default)]
+    pub scopes: Vec<String>,
 }
 
 impl TwitchHelixClient {
@@ -0,0 +1,179 @@
+// File: maowbot-core/src/platforms/twitch/device_code.rs
+//
+// Twitch Device Code Grant (https://dev.twitch.tv/docs/authentication/getting-tokens-oauth/#device-code-grant-flow).
+// Lets a headless host log in: we show a short code + URL, the user approves it
+// from any browser, and we poll the token endpoint until it is granted.
+// Shared by the Helix and IRC authenticators.
+
+use chrono::{DateTime, Utc};
+use reqwest::Client as ReqwestClient;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::Error;
+use maowbot_common::models::auth::AuthenticationPrompt;
+
+const DEVICE_URL: &str = "https://id.twitch.tv/oauth2/device";
+const TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+#[derive(Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    expires_in: u64,
+    interval: u64,
+    user_code: String,
+    verification_uri: String,
+}
+
+/// Error body returned by the token endpoint while polling.
+#[derive(Deserialize)]
+struct DeviceErrorResponse {
+    #[serde(default)]
+    message: String,
+}
+
+/// A device code waiting for the user to approve it.
+#[derive(Debug, Clone)]
+pub struct PendingDeviceCode {
+    device_code: String,
+    scopes: String,
+    pub interval: u64,
+    pub expires_at: DateTime<Utc>,
+    last_poll: Option<DateTime<Utc>>,
+}
+
+/// Asks Twitch for a device code covering `scopes`.
+pub async fn request_device_code(
+    client_id: &str,
+    scopes: &[&str],
+) -> Result<(AuthenticationPrompt, PendingDeviceCode), Error> {
+    let scope_str = scopes.join(" ");
+    let params = [
+        ("client_id", client_id.to_string()),
+        ("scopes", scope_str.clone()),
+    ];
+
+    let resp = ReqwestClient::new()
+        .post(DEVICE_URL)
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| Error::Auth(format!("HTTP error requesting device code: {e}")))?
+        .error_for_status()
+        .map_err(|e| Error::Auth(format!("Twitch device endpoint error: {e}")))?
+        .json::<DeviceCodeResponse>()
+        .await
+        .map_err(|e| Error::Auth(format!("Parse error on device code JSON: {e}")))?;
+
+    let pending = PendingDeviceCode {
+        device_code: resp.device_code,
+        scopes: scope_str,
+        interval: resp.interval,
+        expires_at: Utc::now() + chrono::Duration::seconds(resp.expires_in as i64),
+        last_poll: None,
+    };
+    let prompt = AuthenticationPrompt::DeviceCode {
+        user_code: resp.user_code,
+        verification_uri: resp.verification_uri,
+        expires_in: resp.expires_in,
+        interval: resp.interval,
+    };
+    Ok((prompt, pending))
+}
+
+/// Polls the token endpoint once. Returns `Ok(None)` while the user hasn't
+/// approved the code yet. Calls made sooner than the poll interval return
+/// `Ok(None)` without contacting Twitch; `slow_down` lengthens the interval.
+pub async fn poll_device_token<T: DeserializeOwned>(
+    client_id: &str,
+    pending: &mut PendingDeviceCode,
+) -> Result<Option<T>, Error> {
+    let now = Utc::now();
+    if now >= pending.expires_at {
+        return Err(Error::Auth("Device code expired; start the login again".into()));
+    }
+    if let Some(last) = pending.last_poll {
+        if now < last + chrono::Duration::seconds(pending.interval as i64) {
+            return Ok(None);
+        }
+    }
+    pending.last_poll = Some(now);
+
+    let params = [
+        ("client_id", client_id.to_string()),
+        ("scopes", pending.scopes.clone()),
+        ("device_code", pending.device_code.clone()),
+        ("grant_type", DEVICE_GRANT_TYPE.to_string()),
+    ];
+
+    let resp = ReqwestClient::new()
+        .post(TOKEN_URL)
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| Error::Auth(format!("HTTP error polling device token: {e}")))?;
+
+    if resp.status().is_success() {
+        let token = resp
+            .json::<T>()
+            .await
+            .map_err(|e| Error::Auth(format!("Parse error on token JSON: {e}")))?;
+        return Ok(Some(token));
+    }
+
+    let status = resp.status();
+    let body = resp.text().await.unwrap_or_default();
+    match classify_poll_error(&body) {
+        PollError::Pending => Ok(None),
+        PollError::SlowDown => {
+            pending.interval += 5;
+            debug!("Twitch asked us to slow down device polling; interval now {}s", pending.interval);
+            Ok(None)
+        }
+        PollError::Fatal(message) => Err(Error::Auth(format!(
+            "Device login failed (HTTP {}): {}", status, message
+        ))),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum PollError {
+    Pending,
+    SlowDown,
+    Fatal(String),
+}
+
+fn classify_poll_error(body: &str) -> PollError {
+    let message = serde_json::from_str::<DeviceErrorResponse>(body)
+        .map(|e| e.message)
+        .unwrap_or_else(|_| body.to_string());
+    match message.as_str() {
+        "authorization_pending" => PollError::Pending,
+        "slow_down" => PollError::SlowDown,
+        _ => PollError::Fatal(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_poll_error() {
+        assert_eq!(
+            classify_poll_error(r#"{"status":400,"message":"authorization_pending"}"#),
+            PollError::Pending
+        );
+        assert_eq!(
+            classify_poll_error(r#"{"status":400,"message":"slow_down"}"#),
+            PollError::SlowDown
+        );
+        assert_eq!(
+            classify_poll_error(r#"{"status":400,"message":"invalid device code"}"#),
+            PollError::Fatal("invalid device code".to_string())
+        );
+        assert_eq!(classify_poll_error("bad gateway"), PollError::Fatal("bad gateway".to_string()));
+    }
+}
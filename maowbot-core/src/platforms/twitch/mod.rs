@@ -3,6 +3,8 @@
 pub mod auth;
 pub mod runtime;
 pub mod client;
+pub mod device_code;
+pub mod routing;
 
 // NEW: add a requests submodule directory
 pub mod requests;
@@ -20,17 +20,6 @@ struct BanRequestData<'a> {
     reason:    Option<&'a str>,      //
 }
 
-/// Partial response struct (we don’t need every field).
-#[derive(Debug, Deserialize)]
-struct BanResponse {
-    data: Vec<BanResult>,
-}
-#[derive(Debug, Deserialize)]
-struct BanResult {
-    user_id: String,
-    end_time: Option<String>,
-}
-
 impl TwitchHelixClient {
     /// Ban or timeout a user.
     ///
@@ -71,6 +60,35 @@ impl TwitchHelixClient {
         Ok(())
     }
 
+    /// Lift a ban or timeout (Helix ⟶ DELETE /moderation/bans).
+    pub async fn unban_user(
+        &self,
+        broadcaster_id: &str,
+        moderator_id:   &str,
+        user_id:        &str,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "https://api.twitch.tv/helix/moderation/bans?broadcaster_id={}&moderator_id={}&user_id={}",
+            broadcaster_id, moderator_id, user_id
+        );
+
+        let resp = self
+            .http_client()
+            .delete(&url)
+            .header("Client-Id",  self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("unban_user network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text   = resp.text().await.unwrap_or_default();
+            return Err(Error::Platform(format!("unban_user: HTTP {status} ⇒ {text}")));
+        }
+        Ok(())
+    }
+
     /// Resolve login → user‑id (cheap helper for mod tools).
     pub async fn fetch_user_id(&self, login: &str) -> Result<Option<String>, Error> {
         let url = format!("https://api.twitch.tv/helix/users?login={}", login.to_lowercase());
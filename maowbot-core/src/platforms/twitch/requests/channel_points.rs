@@ -114,7 +114,7 @@ pub struct CustomRewardBody {
 }
 
 /// Redemption object returned by "Get Custom Reward Redemption" calls or updates.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Redemption {
     pub broadcaster_id: String,
     pub broadcaster_login: Option<String>,
@@ -129,7 +129,7 @@ pub struct Redemption {
     pub reward: RedemptionReward,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RedemptionReward {
     pub id: String,
     pub title: String,
@@ -0,0 +1,78 @@
+// File: maowbot-core/src/platforms/twitch/requests/chatters.rs
+
+use serde::Deserialize;
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+
+/// Largest page `GET /helix/chat/chatters` returns.
+const PAGE_SIZE: u32 = 1000;
+
+#[derive(Debug, Deserialize)]
+struct ChattersResponse {
+    data: Vec<Chatter>,
+    #[serde(default)]
+    pagination: Pagination,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Pagination {
+    cursor: Option<String>,
+}
+
+/// A single record from `GET /helix/chat/chatters`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Chatter {
+    pub user_id: String,
+    /// All-lowercase login
+    pub user_login: String,
+    /// Display name
+    pub user_name: String,
+}
+
+impl TwitchHelixClient {
+    /// Everyone connected to `broadcaster_id`'s chat, across all pages.
+    ///
+    /// Requires a user token with `moderator:read:chatters`; `moderator_id`
+    /// must be the token's user and either the broadcaster or one of their
+    /// moderators. Twitch updates the list every few minutes and leaves out
+    /// some viewers in very large channels.
+    pub async fn get_chatters(
+        &self,
+        broadcaster_id: &str,
+        moderator_id: &str,
+    ) -> Result<Vec<Chatter>, Error> {
+        let mut chatters = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut request = self
+                .http_client()
+                .get("https://api.twitch.tv/helix/chat/chatters")
+                .header("Client-Id", self.client_id())
+                .header("Authorization", format!("Bearer {}", self.bearer_token()))
+                .query(&[("broadcaster_id", broadcaster_id), ("moderator_id", moderator_id)])
+                .query(&[("first", PAGE_SIZE)]);
+            if let Some(after) = &cursor {
+                request = request.query(&[("after", after)]);
+            }
+            let resp = request
+                .send()
+                .await
+                .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+            if !resp.status().is_success() {
+                return Err(Self::helix_error(resp, "get_chatters").await);
+            }
+
+            let page: ChattersResponse = resp
+                .json()
+                .await
+                .map_err(|e| Error::Platform(format!("Error parsing /chat/chatters JSON: {e}")))?;
+            chatters.extend(page.data);
+            match page.pagination.cursor.filter(|c| !c.is_empty()) {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        Ok(chatters)
+    }
+}
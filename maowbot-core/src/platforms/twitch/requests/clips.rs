@@ -0,0 +1,122 @@
+// File: maowbot-core/src/platforms/twitch/requests/clips.rs
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+
+#[derive(Debug, Deserialize)]
+struct CreateClipResponse {
+    data: Vec<CreatedClip>,
+}
+
+/// Clip returned by `POST /helix/clips`; Twitch finishes processing it
+/// asynchronously, so it may not be listed by `GET /helix/clips` right away.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatedClip {
+    pub id: String,
+    pub edit_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClipsResponse {
+    data: Vec<ClipData>,
+}
+
+/// A single record from `GET /helix/clips`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClipData {
+    pub id: String,
+    pub url: String,
+    pub broadcaster_id: String,
+    pub broadcaster_name: String,
+    pub creator_name: String,
+    #[serde(default)]
+    pub video_id: String,
+    pub title: String,
+    pub view_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub thumbnail_url: String,
+    /// Seconds
+    pub duration: f64,
+}
+
+impl TwitchHelixClient {
+    /// Clips the last seconds of `broadcaster_id`'s live stream.
+    ///
+    /// Requires a user token with `clips:edit`. Fails when the channel is
+    /// offline or has clips disabled.
+    pub async fn create_clip(&self, broadcaster_id: &str) -> Result<CreatedClip, Error> {
+        let resp = self
+            .http_client()
+            .post(format!("https://api.twitch.tv/helix/clips?broadcaster_id={}", broadcaster_id))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "create_clip").await);
+        }
+
+        let parsed: CreateClipResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /clips JSON: {e}")))?;
+        parsed.data.into_iter().next()
+            .ok_or_else(|| Error::Platform("Twitch returned no clip".into()))
+    }
+
+    /// Clips of `broadcaster_id` made since `since`, most viewed first (max 100).
+    pub async fn fetch_clips_since(
+        &self,
+        broadcaster_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ClipData>, Error> {
+        let resp = self
+            .http_client()
+            .get(format!(
+                "https://api.twitch.tv/helix/clips?broadcaster_id={}&started_at={}&first=100",
+                broadcaster_id,
+                since.format("%Y-%m-%dT%H:%M:%SZ")
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_clips_since").await);
+        }
+
+        let parsed: ClipsResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /clips JSON: {e}")))?;
+        Ok(parsed.data)
+    }
+
+    /// One clip by id, or None while Twitch is still processing it.
+    pub async fn fetch_clip(&self, clip_id: &str) -> Result<Option<ClipData>, Error> {
+        let resp = self
+            .http_client()
+            .get(format!("https://api.twitch.tv/helix/clips?id={}", clip_id))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_clip").await);
+        }
+
+        let parsed: ClipsResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /clips JSON: {e}")))?;
+        Ok(parsed.data.into_iter().next())
+    }
+}
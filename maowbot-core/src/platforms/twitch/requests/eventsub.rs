@@ -0,0 +1,175 @@
+// File: maowbot-core/src/platforms/twitch/requests/eventsub.rs
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionsResponse {
+    data: Vec<EventSubSubscription>,
+    #[serde(default)]
+    total_cost: i64,
+    #[serde(default)]
+    max_total_cost: i64,
+    #[serde(default)]
+    pagination: Pagination,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Pagination {
+    cursor: Option<String>,
+}
+
+/// A single record from `GET /helix/eventsub/subscriptions`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventSubSubscription {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub sub_type: String,
+    pub version: String,
+    /// "enabled", or why it isn't: "websocket_disconnected", "authorization_revoked", ...
+    pub status: String,
+    /// Counts against `max_total_cost`; 0 when the user in the condition authorized the app
+    #[serde(default)]
+    pub cost: i64,
+    /// Twitch fills in every condition field the type has, unused ones as ""
+    #[serde(default)]
+    pub condition: Value,
+    #[serde(default)]
+    pub transport: EventSubTransport,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventSubTransport {
+    /// "websocket", "webhook" or "conduit"
+    #[serde(default)]
+    pub method: String,
+    pub session_id: Option<String>,
+}
+
+/// Every subscription of a client ID, with what they cost together.
+#[derive(Debug, Clone, Default)]
+pub struct EventSubSubscriptions {
+    pub subscriptions: Vec<EventSubSubscription>,
+    pub total_cost: i64,
+    pub max_total_cost: i64,
+}
+
+/// What `create_eventsub_subscription` did.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum CreatedSubscription {
+    Created {
+        subscription: EventSubSubscription,
+        total_cost: i64,
+        max_total_cost: i64,
+    },
+    /// The same type, version and condition already exists on this transport
+    AlreadyExists,
+}
+
+impl TwitchHelixClient {
+    /// Every EventSub subscription made with this token's client ID, across all pages.
+    pub async fn list_eventsub_subscriptions(&self) -> Result<EventSubSubscriptions, Error> {
+        let mut all = EventSubSubscriptions::default();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut request = self
+                .http_client()
+                .get("https://api.twitch.tv/helix/eventsub/subscriptions")
+                .header("Client-Id", self.client_id())
+                .header("Authorization", format!("Bearer {}", self.bearer_token()));
+            if let Some(after) = &cursor {
+                request = request.query(&[("after", after)]);
+            }
+            let resp = request
+                .send()
+                .await
+                .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+            if !resp.status().is_success() {
+                return Err(Self::helix_error(resp, "list_eventsub_subscriptions").await);
+            }
+
+            let page: SubscriptionsResponse = resp
+                .json()
+                .await
+                .map_err(|e| Error::Platform(format!("Error parsing /eventsub/subscriptions JSON: {e}")))?;
+            all.subscriptions.extend(page.data);
+            all.total_cost = page.total_cost;
+            all.max_total_cost = page.max_total_cost;
+            match page.pagination.cursor.filter(|c| !c.is_empty()) {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        Ok(all)
+    }
+
+    /// Subscribes the websocket session `session_id` to `sub_type`.
+    pub async fn create_eventsub_subscription(
+        &self,
+        sub_type: &str,
+        version: &str,
+        condition: &Value,
+        session_id: &str,
+    ) -> Result<CreatedSubscription, Error> {
+        let body = json!({
+            "type": sub_type,
+            "version": version,
+            "condition": condition,
+            "transport": { "method": "websocket", "session_id": session_id },
+        });
+        let resp = self
+            .http_client()
+            .post("https://api.twitch.tv/helix/eventsub/subscriptions")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if resp.status() == StatusCode::CONFLICT {
+            return Ok(CreatedSubscription::AlreadyExists);
+        }
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "create_eventsub_subscription").await);
+        }
+
+        let created: SubscriptionsResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /eventsub/subscriptions JSON: {e}")))?;
+        let subscription = created.data.into_iter().next()
+            .ok_or_else(|| Error::Platform("Twitch created no subscription".into()))?;
+        Ok(CreatedSubscription::Created {
+            subscription,
+            total_cost: created.total_cost,
+            max_total_cost: created.max_total_cost,
+        })
+    }
+
+    /// Deletes a subscription; one that is already gone counts as deleted.
+    pub async fn delete_eventsub_subscription(&self, id: &str) -> Result<(), Error> {
+        let resp = self
+            .http_client()
+            .delete("https://api.twitch.tv/helix/eventsub/subscriptions")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .query(&[("id", id)])
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(Self::helix_error(resp, "delete_eventsub_subscription").await)
+        }
+    }
+}
@@ -0,0 +1,137 @@
+// File: maowbot-core/src/platforms/twitch/requests/markers.rs
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::warn;
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::platforms::twitch::requests::stream::{StreamData, StreamsResponse};
+
+/// Helix rejects longer marker descriptions.
+pub const MAX_MARKER_DESCRIPTION: usize = 140;
+
+#[derive(Debug, Deserialize)]
+struct CreateMarkerResponse {
+    data: Vec<CreatedMarker>,
+}
+
+/// Marker returned by `POST /helix/streams/markers`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatedMarker {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub description: String,
+    pub position_seconds: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct VideosResponse {
+    data: Vec<VideoData>,
+}
+
+/// A single record from `GET /helix/videos`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VideoData {
+    pub id: String,
+    /// Only set for archives (past broadcasts)
+    pub stream_id: Option<String>,
+    pub user_id: String,
+    pub title: String,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+    pub duration: String,
+}
+
+impl TwitchHelixClient {
+    pub(crate) async fn helix_error(resp: reqwest::Response, what: &str) -> Error {
+        let status = resp.status();
+        let body_text = resp.text().await.unwrap_or_default();
+        warn!("{} => status={} body={}", what, status, body_text);
+        Error::Platform(format!("Twitch API error: HTTP {} => {}", status, body_text))
+    }
+
+    /// Places a marker at the current point of `user_id`'s live broadcast.
+    ///
+    /// Requires a user token with `channel:manage:broadcast`. Fails when the
+    /// channel is offline or has VODs disabled.
+    pub async fn create_stream_marker(
+        &self,
+        user_id: &str,
+        description: &str,
+    ) -> Result<CreatedMarker, Error> {
+        let description: String = description.chars().take(MAX_MARKER_DESCRIPTION).collect();
+        let resp = self
+            .http_client()
+            .post("https://api.twitch.tv/helix/streams/markers")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .json(&json!({ "user_id": user_id, "description": description }))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "create_stream_marker").await);
+        }
+
+        let parsed: CreateMarkerResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /streams/markers JSON: {e}")))?;
+        parsed.data.into_iter().next()
+            .ok_or_else(|| Error::Platform("Twitch returned no marker".into()))
+    }
+
+    /// The live stream of `user_id`, or None when offline.
+    pub async fn fetch_live_stream(&self, user_id: &str) -> Result<Option<StreamData>, Error> {
+        let resp = self
+            .http_client()
+            .get(format!("https://api.twitch.tv/helix/streams?user_id={}", user_id))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_live_stream").await);
+        }
+
+        let parsed: StreamsResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /streams JSON: {e}")))?;
+        Ok(parsed.data.into_iter().next())
+    }
+
+    /// The archived VOD of broadcast `stream_id`, if Twitch kept one.
+    pub async fn fetch_archive_video(
+        &self,
+        user_id: &str,
+        stream_id: &str,
+    ) -> Result<Option<VideoData>, Error> {
+        let resp = self
+            .http_client()
+            .get(format!(
+                "https://api.twitch.tv/helix/videos?user_id={}&type=archive&first=20",
+                user_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_archive_video").await);
+        }
+
+        let parsed: VideosResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /videos JSON: {e}")))?;
+        Ok(parsed.data.into_iter().find(|v| v.stream_id.as_deref() == Some(stream_id)))
+    }
+}
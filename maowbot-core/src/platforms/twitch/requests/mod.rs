@@ -1,6 +1,14 @@
 // File: maowbot-core/src/platforms/twitch/requests/mod.rs
 pub mod channel_points;
+pub mod chatters;
+pub mod clips;
+pub mod eventsub;
 pub mod follow;
+pub mod markers;
+pub mod moderation;
+pub mod schedule;
 pub mod stream;
+pub mod subscriptions;
 pub mod ban;
 pub mod token;
+pub mod whispers;
@@ -0,0 +1,224 @@
+// File: maowbot-core/src/platforms/twitch/requests/moderation.rs
+//
+// Chat lockdown controls: Shield Mode, chat settings (follower-only / slow
+// mode), account ages for raid detection, and message deletion.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+
+/// Helix accepts at most this many ids per `/users` lookup.
+const MAX_USERS_PER_LOOKUP: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct DataResponse<T> {
+    data: Vec<T>,
+}
+
+/// The chat modes we switch during a lockdown, from `GET /helix/chat/settings`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChatSettings {
+    pub follower_mode: bool,
+    /// Minutes a viewer must have followed; only meaningful with follower mode
+    pub follower_mode_duration: Option<u32>,
+    pub slow_mode: bool,
+    /// Seconds between messages; only meaningful with slow mode
+    pub slow_mode_wait_time: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShieldModeStatus {
+    is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserCreated {
+    id: String,
+    created_at: DateTime<Utc>,
+}
+
+impl TwitchHelixClient {
+    /// Turns Shield Mode on or off.
+    ///
+    /// Requires `moderator:manage:shield_mode`.
+    pub async fn set_shield_mode(
+        &self,
+        broadcaster_id: &str,
+        moderator_id: &str,
+        active: bool,
+    ) -> Result<(), Error> {
+        let resp = self
+            .http_client()
+            .put(format!(
+                "https://api.twitch.tv/helix/moderation/shield_mode?broadcaster_id={}&moderator_id={}",
+                broadcaster_id, moderator_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .json(&json!({ "is_active": active }))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "set_shield_mode").await);
+        }
+        Ok(())
+    }
+
+    /// Whether Shield Mode is currently on.
+    pub async fn fetch_shield_mode(
+        &self,
+        broadcaster_id: &str,
+        moderator_id: &str,
+    ) -> Result<bool, Error> {
+        let resp = self
+            .http_client()
+            .get(format!(
+                "https://api.twitch.tv/helix/moderation/shield_mode?broadcaster_id={}&moderator_id={}",
+                broadcaster_id, moderator_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_shield_mode").await);
+        }
+        let parsed: DataResponse<ShieldModeStatus> = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /moderation/shield_mode JSON: {e}")))?;
+        Ok(parsed.data.first().is_some_and(|s| s.is_active))
+    }
+
+    pub async fn fetch_chat_settings(
+        &self,
+        broadcaster_id: &str,
+        moderator_id: &str,
+    ) -> Result<ChatSettings, Error> {
+        let resp = self
+            .http_client()
+            .get(format!(
+                "https://api.twitch.tv/helix/chat/settings?broadcaster_id={}&moderator_id={}",
+                broadcaster_id, moderator_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_chat_settings").await);
+        }
+        let parsed: DataResponse<ChatSettings> = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /chat/settings JSON: {e}")))?;
+        parsed.data.into_iter().next()
+            .ok_or_else(|| Error::Platform("Twitch returned no chat settings".into()))
+    }
+
+    /// Applies follower-only and slow mode as given.
+    ///
+    /// Requires `moderator:manage:chat_settings`.
+    pub async fn update_chat_settings(
+        &self,
+        broadcaster_id: &str,
+        moderator_id: &str,
+        settings: &ChatSettings,
+    ) -> Result<(), Error> {
+        let mut body = json!({
+            "follower_mode": settings.follower_mode,
+            "slow_mode": settings.slow_mode,
+        });
+        if settings.follower_mode {
+            body["follower_mode_duration"] = json!(settings.follower_mode_duration.unwrap_or(0));
+        }
+        if settings.slow_mode {
+            body["slow_mode_wait_time"] = json!(settings.slow_mode_wait_time.unwrap_or(30));
+        }
+
+        let resp = self
+            .http_client()
+            .patch(format!(
+                "https://api.twitch.tv/helix/chat/settings?broadcaster_id={}&moderator_id={}",
+                broadcaster_id, moderator_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "update_chat_settings").await);
+        }
+        Ok(())
+    }
+
+    /// Deletes one chat message by its IRC `id` tag.
+    ///
+    /// Requires `moderator:manage:chat_messages`.
+    pub async fn delete_chat_message(
+        &self,
+        broadcaster_id: &str,
+        moderator_id: &str,
+        message_id: &str,
+    ) -> Result<(), Error> {
+        let resp = self
+            .http_client()
+            .delete(format!(
+                "https://api.twitch.tv/helix/moderation/chat?broadcaster_id={}&moderator_id={}&message_id={}",
+                broadcaster_id, moderator_id, message_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "delete_chat_message").await);
+        }
+        Ok(())
+    }
+
+    /// Account creation times for the given Twitch user ids. Unknown or
+    /// deleted accounts are left out.
+    pub async fn fetch_account_created(
+        &self,
+        user_ids: &[String],
+    ) -> Result<HashMap<String, DateTime<Utc>>, Error> {
+        let mut created = HashMap::new();
+        for chunk in user_ids.chunks(MAX_USERS_PER_LOOKUP) {
+            let query: Vec<(&str, &str)> = chunk.iter().map(|id| ("id", id.as_str())).collect();
+            let resp = self
+                .http_client()
+                .get("https://api.twitch.tv/helix/users")
+                .query(&query)
+                .header("Client-Id", self.client_id())
+                .header("Authorization", format!("Bearer {}", self.bearer_token()))
+                .send()
+                .await
+                .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+            if !resp.status().is_success() {
+                return Err(Self::helix_error(resp, "fetch_account_created").await);
+            }
+            let parsed: DataResponse<UserCreated> = resp
+                .json()
+                .await
+                .map_err(|e| Error::Platform(format!("Error parsing /users JSON: {e}")))?;
+            created.extend(parsed.data.into_iter().map(|u| (u.id, u.created_at)));
+        }
+        Ok(created)
+    }
+}
@@ -0,0 +1,210 @@
+// File: maowbot-core/src/platforms/twitch/requests/schedule.rs
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::platforms::twitch::requests::stream::{GameData, GamesResponse};
+
+/// Largest page `GET /helix/schedule` returns.
+const PAGE_SIZE: u32 = 25;
+
+#[derive(Debug, Deserialize)]
+struct ScheduleResponse {
+    data: ScheduleData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScheduleData {
+    /// Null when the schedule is empty
+    segments: Option<Vec<ScheduleSegment>>,
+}
+
+/// The game or category a segment is planned for.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScheduleCategory {
+    pub id: String,
+    pub name: String,
+}
+
+/// A single broadcast from `GET /helix/schedule`. Recurring segments show up
+/// once per occurrence, all with the same id.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScheduleSegment {
+    pub id: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub title: String,
+    /// Set when this occurrence is canceled
+    pub canceled_until: Option<DateTime<Utc>>,
+    pub category: Option<ScheduleCategory>,
+    pub is_recurring: bool,
+}
+
+/// Body of `POST /helix/schedule/segment`.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewScheduleSegment {
+    pub start_time: DateTime<Utc>,
+    /// IANA time zone, e.g. "Europe/Berlin"; recurring segments follow its DST
+    pub timezone: String,
+    /// Minutes, as Twitch expects a string
+    pub duration: String,
+    pub is_recurring: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<String>,
+    pub title: String,
+}
+
+/// Body of `PATCH /helix/schedule/segment`; fields left None are unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScheduleSegmentUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_canceled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl TwitchHelixClient {
+    /// The next (up to 25) segments of `broadcaster_id`'s stream schedule,
+    /// starting now. Empty when they have no schedule.
+    pub async fn get_channel_schedule(&self, broadcaster_id: &str) -> Result<Vec<ScheduleSegment>, Error> {
+        let resp = self
+            .http_client()
+            .get("https://api.twitch.tv/helix/schedule")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .query(&[("broadcaster_id", broadcaster_id)])
+            .query(&[("first", PAGE_SIZE)])
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        // Twitch answers 404 for a channel that never set up a schedule
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "get_channel_schedule").await);
+        }
+
+        let parsed: ScheduleResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /schedule JSON: {e}")))?;
+        Ok(parsed.data.segments.unwrap_or_default())
+    }
+
+    /// Adds a segment to the broadcaster's schedule.
+    ///
+    /// Requires a user token with `channel:manage:schedule`.
+    pub async fn create_schedule_segment(
+        &self,
+        broadcaster_id: &str,
+        segment: &NewScheduleSegment,
+    ) -> Result<ScheduleSegment, Error> {
+        let resp = self
+            .http_client()
+            .post("https://api.twitch.tv/helix/schedule/segment")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .query(&[("broadcaster_id", broadcaster_id)])
+            .json(segment)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "create_schedule_segment").await);
+        }
+        Self::first_segment(resp).await
+    }
+
+    /// Changes a segment; a recurring segment changes every occurrence.
+    ///
+    /// Requires a user token with `channel:manage:schedule`.
+    pub async fn update_schedule_segment(
+        &self,
+        broadcaster_id: &str,
+        segment_id: &str,
+        update: &ScheduleSegmentUpdate,
+    ) -> Result<ScheduleSegment, Error> {
+        let resp = self
+            .http_client()
+            .patch("https://api.twitch.tv/helix/schedule/segment")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .query(&[("broadcaster_id", broadcaster_id), ("id", segment_id)])
+            .json(update)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "update_schedule_segment").await);
+        }
+        Self::first_segment(resp).await
+    }
+
+    /// Removes a segment (every occurrence, for a recurring one).
+    ///
+    /// Requires a user token with `channel:manage:schedule`.
+    pub async fn delete_schedule_segment(&self, broadcaster_id: &str, segment_id: &str) -> Result<(), Error> {
+        let resp = self
+            .http_client()
+            .delete("https://api.twitch.tv/helix/schedule/segment")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .query(&[("broadcaster_id", broadcaster_id), ("id", segment_id)])
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "delete_schedule_segment").await);
+        }
+        Ok(())
+    }
+
+    /// The category named exactly `name` (case-insensitive), if Twitch has one.
+    pub async fn fetch_game_by_name(&self, name: &str) -> Result<Option<GameData>, Error> {
+        let resp = self
+            .http_client()
+            .get("https://api.twitch.tv/helix/games")
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .query(&[("name", name)])
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "fetch_game_by_name").await);
+        }
+
+        let parsed: GamesResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /games JSON: {e}")))?;
+        Ok(parsed.data.into_iter().next())
+    }
+
+    async fn first_segment(resp: reqwest::Response) -> Result<ScheduleSegment, Error> {
+        let parsed: ScheduleResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /schedule/segment JSON: {e}")))?;
+        parsed.data.segments
+            .and_then(|segments| segments.into_iter().next())
+            .ok_or_else(|| Error::Platform("Twitch returned no schedule segment".into()))
+    }
+}
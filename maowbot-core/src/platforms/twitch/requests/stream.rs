@@ -2,7 +2,7 @@
 // File: maowbot-core/src/platforms/twitch/requests/stream.rs
 // ========================================================
 use serde::Deserialize;
-use tracing::{debug, warn};
+use tracing::debug;
 
 use crate::Error;
 use crate::platforms::twitch::client::TwitchHelixClient;
@@ -212,3 +212,43 @@ pub async fn fetch_stream_details(
         pfp,
     })
 }
+
+#[derive(Debug, Deserialize)]
+struct ChannelInformationResponse {
+    data: Vec<ChannelInformation>,
+}
+
+/// A single record from `GET /helix/channels`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelInformation {
+    pub broadcaster_id: String,
+    pub broadcaster_login: String,
+    /// The current category, e.g. "Just Chatting"; empty when none is set
+    pub game_name: String,
+    pub game_id: String,
+    pub title: String,
+}
+
+impl TwitchHelixClient {
+    /// The channel's title and category, live or not.
+    pub async fn get_channel_information(&self, broadcaster_id: &str) -> Result<Option<ChannelInformation>, Error> {
+        let resp = self
+            .http_client()
+            .get(format!("https://api.twitch.tv/helix/channels?broadcaster_id={}", broadcaster_id))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "get_channel_information").await);
+        }
+
+        let parsed: ChannelInformationResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /channels JSON: {e}")))?;
+        Ok(parsed.data.into_iter().next())
+    }
+}
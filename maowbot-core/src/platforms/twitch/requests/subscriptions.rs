@@ -0,0 +1,63 @@
+// File: maowbot-core/src/platforms/twitch/requests/subscriptions.rs
+
+use serde::Deserialize;
+use tracing::warn;
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionsResponse {
+    data: Vec<SubscriptionData>,
+}
+
+/// A single record from `GET /helix/subscriptions`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubscriptionData {
+    pub user_id: String,
+    pub user_login: String,
+    /// "1000", "2000" or "3000"
+    pub tier: String,
+    pub is_gift: bool,
+}
+
+impl TwitchHelixClient {
+    /// Returns `user_id`'s subscription to `broadcaster_id`, or None if they
+    /// aren't subscribed.
+    ///
+    /// Requires the broadcaster's token with `channel:read:subscriptions`.
+    pub async fn fetch_subscription(
+        &self,
+        broadcaster_id: &str,
+        user_id: &str,
+    ) -> Result<Option<SubscriptionData>, Error> {
+        let url = format!(
+            "https://api.twitch.tv/helix/subscriptions?broadcaster_id={}&user_id={}",
+            broadcaster_id, user_id
+        );
+
+        let resp = self
+            .http_client()
+            .get(&url)
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body_text = resp.text().await.unwrap_or_default();
+            warn!("fetch_subscription => status={} body={}", status, body_text);
+            return Err(Error::Platform(format!(
+                "Twitch API error: HTTP {} => {}",
+                status, body_text
+            )));
+        }
+
+        let parsed: SubscriptionsResponse = resp
+            .json()
+            .await
+            .map_err(|e| Error::Platform(format!("Error parsing /subscriptions JSON: {e}")))?;
+        Ok(parsed.data.into_iter().next())
+    }
+}
@@ -0,0 +1,40 @@
+// File: maowbot-core/src/platforms/twitch/requests/whispers.rs
+
+use serde_json::json;
+use crate::Error;
+use crate::platforms::twitch::client::TwitchHelixClient;
+
+/// Helix truncates longer whispers to new recipients; keep every whisper under it.
+pub const MAX_WHISPER_LEN: usize = 500;
+
+impl TwitchHelixClient {
+    /// Whispers `message` from `from_user_id` (the token's owner) to `to_user_id`.
+    ///
+    /// Requires `user:manage:whispers` and a verified phone number on the
+    /// sending account. Twitch rate-limits whispers to new recipients.
+    pub async fn send_whisper(
+        &self,
+        from_user_id: &str,
+        to_user_id: &str,
+        message: &str,
+    ) -> Result<(), Error> {
+        let message: String = message.chars().take(MAX_WHISPER_LEN).collect();
+        let resp = self
+            .http_client()
+            .post(format!(
+                "https://api.twitch.tv/helix/whispers?from_user_id={}&to_user_id={}",
+                from_user_id, to_user_id
+            ))
+            .header("Client-Id", self.client_id())
+            .header("Authorization", format!("Bearer {}", self.bearer_token()))
+            .json(&json!({ "message": message }))
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("Network error: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::helix_error(resp, "send_whisper").await);
+        }
+        Ok(())
+    }
+}
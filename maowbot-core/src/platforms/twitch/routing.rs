@@ -0,0 +1,293 @@
+// File: maowbot-core/src/platforms/twitch/routing.rs
+//
+// Which Twitch account an operation runs as. Chat-facing features (replies,
+// whispers, moderation, clips, shoutouts) speak as the bot account when one
+// is set up, while ads, stream markers, raids, channel point rewards and
+// stream info only work with the broadcaster's own token. The defaults can
+// be changed per operation with `twitch.account_routing`, e.g.
+// {"moderation": "broadcaster", "whispers": "bot_only"}. Routes Helix can't
+// honour are rejected, and a missing account fails with what to set up
+// instead of an opaque 401.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+pub const ROUTING_SETTING: &str = "twitch.account_routing";
+
+/// Something the bot does on Twitch that needs an account's token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TwitchOperation {
+    Chat,
+    Whispers,
+    Moderation,
+    Clips,
+    Shoutouts,
+    Ads,
+    Markers,
+    Raids,
+    Redeems,
+    StreamInfo,
+}
+
+impl TwitchOperation {
+    pub const ALL: [TwitchOperation; 10] = [
+        TwitchOperation::Chat,
+        TwitchOperation::Whispers,
+        TwitchOperation::Moderation,
+        TwitchOperation::Clips,
+        TwitchOperation::Shoutouts,
+        TwitchOperation::Ads,
+        TwitchOperation::Markers,
+        TwitchOperation::Raids,
+        TwitchOperation::Redeems,
+        TwitchOperation::StreamInfo,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TwitchOperation::Chat => "chat",
+            TwitchOperation::Whispers => "whispers",
+            TwitchOperation::Moderation => "moderation",
+            TwitchOperation::Clips => "clips",
+            TwitchOperation::Shoutouts => "shoutouts",
+            TwitchOperation::Ads => "ads",
+            TwitchOperation::Markers => "markers",
+            TwitchOperation::Raids => "raids",
+            TwitchOperation::Redeems => "redeems",
+            TwitchOperation::StreamInfo => "stream_info",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<TwitchOperation> {
+        Self::ALL.into_iter().find(|op| op.as_str().eq_ignore_ascii_case(s))
+    }
+
+    /// Helix only accepts the broadcaster's own token for these.
+    pub fn broadcaster_only(self) -> bool {
+        matches!(
+            self,
+            TwitchOperation::Ads
+                | TwitchOperation::Markers
+                | TwitchOperation::Raids
+                | TwitchOperation::Redeems
+                | TwitchOperation::StreamInfo
+        )
+    }
+
+    /// Chat goes out over IRC; everything else is a Helix call.
+    pub fn platform(self) -> Platform {
+        match self {
+            TwitchOperation::Chat => Platform::TwitchIRC,
+            _ => Platform::Twitch,
+        }
+    }
+
+    fn default_route(self) -> AccountRoute {
+        if self.broadcaster_only() { AccountRoute::Broadcaster } else { AccountRoute::Bot }
+    }
+}
+
+/// Which account an operation should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountRoute {
+    /// The bot account, or the broadcaster when no bot is set up
+    Bot,
+    /// The bot account, failing when there is none
+    BotOnly,
+    Broadcaster,
+}
+
+impl AccountRoute {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccountRoute::Bot => "bot",
+            AccountRoute::BotOnly => "bot_only",
+            AccountRoute::Broadcaster => "broadcaster",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<AccountRoute> {
+        match s.to_ascii_lowercase().as_str() {
+            "bot" => Some(AccountRoute::Bot),
+            "bot_only" => Some(AccountRoute::BotOnly),
+            "broadcaster" => Some(AccountRoute::Broadcaster),
+            _ => None,
+        }
+    }
+}
+
+/// The defaults plus whatever `twitch.account_routing` overrides.
+#[derive(Debug, Clone, Default)]
+pub struct RoutingPolicy {
+    overrides: HashMap<TwitchOperation, AccountRoute>,
+}
+
+impl RoutingPolicy {
+    /// Parses a `{"operation": "bot" | "bot_only" | "broadcaster"}` object.
+    pub fn parse(json: &str) -> Result<RoutingPolicy, Error> {
+        let raw: HashMap<String, String> = serde_json::from_str(json)
+            .map_err(|e| Error::Parse(format!("{}: {}", ROUTING_SETTING, e)))?;
+        let mut overrides = HashMap::new();
+        for (op_name, route) in raw {
+            let op = TwitchOperation::parse(&op_name).ok_or_else(|| Error::ValidationError(format!(
+                "Unknown Twitch operation '{}' in {} (expected one of: {})",
+                op_name,
+                ROUTING_SETTING,
+                TwitchOperation::ALL.map(TwitchOperation::as_str).join(", ")
+            )))?;
+            let route = AccountRoute::parse(&route).ok_or_else(|| Error::ValidationError(format!(
+                "Unknown account '{}' for {} (expected bot, bot_only or broadcaster)", route, op_name
+            )))?;
+            if op.broadcaster_only() && route != AccountRoute::Broadcaster {
+                return Err(Error::ValidationError(format!(
+                    "Twitch only accepts the broadcaster's token for {}", op.as_str()
+                )));
+            }
+            overrides.insert(op, route);
+        }
+        Ok(RoutingPolicy { overrides })
+    }
+
+    pub fn route(&self, op: TwitchOperation) -> AccountRoute {
+        self.overrides.get(&op).copied().unwrap_or_else(|| op.default_route())
+    }
+}
+
+/// The credential an operation resolved to.
+#[derive(Debug, Clone)]
+pub struct RoutedAccount {
+    pub operation: TwitchOperation,
+    pub route: AccountRoute,
+    pub credential: PlatformCredential,
+    /// False when the broadcaster was used, including a "bot" route falling back
+    pub is_bot: bool,
+}
+
+/// A Helix client on the routed account's token.
+pub struct RoutedHelix {
+    pub account: RoutedAccount,
+    /// The acting account's Twitch user id (Helix's moderator_id / from_user_id)
+    pub acting_id: String,
+    pub client: TwitchHelixClient,
+    /// The channel owner's Twitch user id, when a broadcaster is set up
+    pub broadcaster_id: Option<String>,
+}
+
+impl RoutedHelix {
+    /// The broadcaster's id, for operations that act in the broadcaster's channel.
+    pub fn channel_id(&self) -> Result<&str, Error> {
+        self.broadcaster_id.as_deref().ok_or_else(|| missing_account(self.account.operation, AccountRoute::Broadcaster))
+    }
+}
+
+fn missing_account(op: TwitchOperation, route: AccountRoute) -> Error {
+    let platform = op.platform();
+    Error::Platform(match route {
+        AccountRoute::Bot => format!(
+            "Twitch {} runs as the bot account or else the broadcaster, but there is no {} login for either; \
+             add one with `account add {} <name>`",
+            op.as_str(), platform, platform
+        ),
+        AccountRoute::BotOnly => format!(
+            "Twitch {} is routed to the bot account only ({}), but no {} login is marked as a bot; \
+             add one with `account add {} <name>` or mark one with `account type {} <name> bot`",
+            op.as_str(), ROUTING_SETTING, platform, platform, platform
+        ),
+        AccountRoute::Broadcaster => format!(
+            "Twitch {} needs the broadcaster's account, but no {} login is marked as the broadcaster; \
+             add it with `account add {} <name>` or mark it with `account type {} <name> broadcaster`",
+            op.as_str(), platform, platform, platform
+        ),
+    })
+}
+
+fn client_id(cred: &PlatformCredential) -> Result<&str, Error> {
+    cred.additional_data.as_ref()
+        .and_then(|d| d.get("client_id").or_else(|| d.get("validate_client_id")))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::Platform(format!("Twitch credential '{}' missing client_id", cred.user_name)))
+}
+
+/// Picks the credential for each Twitch operation by the routing policy.
+pub struct TwitchAccountRouter {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    settings: Arc<SettingsRegistry>,
+}
+
+impl TwitchAccountRouter {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self { credentials_repo, settings }
+    }
+
+    /// The current policy; an invalid setting is logged and the defaults used.
+    pub fn policy(&self) -> RoutingPolicy {
+        let Some(raw) = self.settings.get(ROUTING_SETTING) else {
+            return RoutingPolicy::default();
+        };
+        RoutingPolicy::parse(&raw).unwrap_or_else(|e| {
+            warn!("Ignoring {}: {}", ROUTING_SETTING, e);
+            RoutingPolicy::default()
+        })
+    }
+
+    /// The credential `op` should run with.
+    pub async fn account_for(&self, op: TwitchOperation) -> Result<RoutedAccount, Error> {
+        let route = self.policy().route(op);
+        let creds = self.credentials_repo.list_credentials_for_platform(&op.platform()).await?;
+        let bot = creds.iter().find(|c| c.is_bot);
+        let broadcaster = creds.iter().find(|c| c.is_broadcaster);
+        let (credential, is_bot) = match (route, bot, broadcaster) {
+            (AccountRoute::Bot | AccountRoute::BotOnly, Some(bot), _) => (bot, true),
+            (AccountRoute::Bot | AccountRoute::Broadcaster, _, Some(broadcaster)) => (broadcaster, false),
+            _ => return Err(missing_account(op, route)),
+        };
+        Ok(RoutedAccount { operation: op, route, credential: credential.clone(), is_bot })
+    }
+
+    /// A Helix client for `op`, with the broadcaster's id for channel operations.
+    pub async fn helix_for(&self, op: TwitchOperation) -> Result<RoutedHelix, Error> {
+        let account = self.account_for(op).await?;
+        let cred = &account.credential;
+        let acting_id = cred.platform_id.clone()
+            .filter(|id| !id.trim().is_empty())
+            .ok_or_else(|| Error::Platform(format!("Twitch credential '{}' missing platform_id", cred.user_name)))?;
+        let client = TwitchHelixClient::new(&cred.primary_token, client_id(cred)?);
+        let broadcaster_id = if cred.is_broadcaster {
+            Some(acting_id.clone())
+        } else {
+            self.credentials_repo.get_broadcaster_credential(&Platform::Twitch).await?
+                .and_then(|b| b.platform_id)
+                .filter(|id| !id.trim().is_empty())
+        };
+        Ok(RoutedHelix { account, acting_id, client, broadcaster_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_policy_rejects_routes_helix_cant_honour() {
+        let policy = RoutingPolicy::parse(r#"{"moderation": "broadcaster", "whispers": "bot_only"}"#).unwrap();
+        assert_eq!(policy.route(TwitchOperation::Moderation), AccountRoute::Broadcaster);
+        assert_eq!(policy.route(TwitchOperation::Whispers), AccountRoute::BotOnly);
+        assert_eq!(policy.route(TwitchOperation::Chat), AccountRoute::Bot);
+        assert_eq!(policy.route(TwitchOperation::Ads), AccountRoute::Broadcaster);
+
+        assert!(RoutingPolicy::parse(r#"{"ads": "bot"}"#).is_err());
+        assert!(RoutingPolicy::parse(r#"{"dancing": "bot"}"#).is_err());
+        assert!(RoutingPolicy::parse(r#"{"chat": "someone"}"#).is_err());
+    }
+}
@@ -98,6 +98,12 @@ pub struct TwitchPlatform {
     pub client: Option<HelixClient<'static, Arc<ReqwestClient>>>,
 }
 
+impl Default for TwitchPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TwitchPlatform {
     /// Example constructor that builds a reqwest Client + HelixClient
     pub fn new() -> Self {
@@ -17,7 +17,6 @@ struct TwitchTokenResponse {
     refresh_token: Option<String>,
     expires_in: u64,
     scope: Option<Vec<String>>,
-    token_type: String,
 }
 
 /// For /validate
@@ -26,7 +25,6 @@ struct TwitchValidateResponse {
     client_id: String,
     login: String,
     user_id: String,
-    expires_in: u64,
 }
 
 /// The TwitchEventSubAuthenticator is meant to reuse Helix logic
@@ -14,6 +14,7 @@ pub mod channel_points;
 pub mod stream_online_offline;
 pub mod update;
 pub mod ad_break;
+pub mod whisper;
 
 pub use base::*;
 pub use ad_break::*;
@@ -29,6 +30,7 @@ pub use shoutout::*;
 pub use channel_points::*;
 pub use stream_online_offline::*;
 pub use update::*;
+pub use whisper::*;
 
 // ------------------------------------------------------------------------
 // The parse_twitch_notification function has been moved here.
@@ -37,6 +39,13 @@ pub use update::*;
 
 use crate::eventbus::TwitchEventSubData;
 
+/// Parse the `payload` of a websocket "notification" message (the
+/// `{ "subscription": { ... }, "event": { ... } }` envelope) into a typed event.
+pub fn parse_notification_payload(payload: &serde_json::Value) -> Option<TwitchEventSubData> {
+    let env = serde_json::from_value::<EventSubNotificationEnvelope>(payload.clone()).ok()?;
+    parse_twitch_notification(&env.subscription.sub_type, &env.event)
+}
+
 /// Helper function to parse from JSON into a known event type. Returns None if unknown.
 pub fn parse_twitch_notification(
     sub_type: &str,
@@ -174,6 +183,10 @@ pub fn parse_twitch_notification(
             serde_json::from_value::<StreamOffline>(event_json.clone()).ok()
             .map(TwitchEventSubData::StreamOffline)
         }
+        "user.whisper.message" => {
+            serde_json::from_value::<UserWhisperMessage>(event_json.clone()).ok()
+                .map(TwitchEventSubData::UserWhisperMessage)
+        }
         _ => None,
     }
 }
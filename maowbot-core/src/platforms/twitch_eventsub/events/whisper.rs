@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// "user.whisper.message" event, received by the subscribed user (`to_user_*`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserWhisperMessage {
+    pub from_user_id: String,
+    pub from_user_login: String,
+    pub from_user_name: String,
+    pub to_user_id: String,
+    pub to_user_login: String,
+    pub to_user_name: String,
+    pub whisper_id: String,
+    pub whisper: WhisperText,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhisperText {
+    pub text: String,
+}
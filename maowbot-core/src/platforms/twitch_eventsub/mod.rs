@@ -3,6 +3,9 @@
 pub mod auth;
 pub mod events;
 pub mod runtime;
+pub mod simulate;
+pub mod subscriptions;
 
 pub use auth::TwitchEventSubAuthenticator;
 pub use runtime::TwitchEventSubPlatform;
+pub use subscriptions::EventSubManager;
@@ -10,9 +10,6 @@ use tokio::net::TcpStream;
 use tracing::{error, info, warn, debug, trace};
 use std::sync::Arc;
 
-use reqwest::Client as ReqwestClient;
-use serde_json::json;
-
 use crate::Error;
 use maowbot_common::models::platform::PlatformCredential;
 
@@ -21,16 +18,22 @@ use maowbot_common::traits::platform_traits::{ConnectionStatus, PlatformAuth, Pl
 use crate::platforms::twitch::requests::token::ensure_valid_token;
 use crate::eventbus::{EventBus, BotEvent};
 
-use super::events::{
-    parse_twitch_notification,
-    EventSubNotificationEnvelope,
-};
+use super::events::parse_notification_payload;
+use super::subscriptions::EventSubManager;
 
 /// TwitchEventSubPlatform holds all relevant state for the websocket session.
 pub struct TwitchEventSubPlatform {
     pub credentials: Option<PlatformCredential>,
     pub connection_status: ConnectionStatus,
     pub event_bus: Option<Arc<EventBus>>,
+    /// Creates this session's subscriptions; a private one if none is set
+    pub subscriptions: Arc<EventSubManager>,
+}
+
+impl Default for TwitchEventSubPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TwitchEventSubPlatform {
@@ -39,6 +42,7 @@ impl TwitchEventSubPlatform {
             credentials: None,
             connection_status: ConnectionStatus::Disconnected,
             event_bus: None,
+            subscriptions: Arc::new(EventSubManager::new()),
         }
     }
 
@@ -46,10 +50,8 @@ impl TwitchEventSubPlatform {
         self.event_bus = Some(event_bus);
     }
 
-    /// Helper method to check if a WebSocket message is a control frame
-    /// (close, ping, or pong).
-    fn is_ws_control(msg: &Message) -> bool {
-        msg.is_close() || msg.is_ping() || msg.is_pong()
+    pub fn set_subscription_manager(&mut self, subscriptions: Arc<EventSubManager>) {
+        self.subscriptions = subscriptions;
     }
 
     /// Helper method to determine if a parsed TEXT message is a health check,
@@ -156,12 +158,14 @@ impl TwitchEventSubPlatform {
                     Ok(None) => {
                         info!("[EventSub] websocket closed gracefully.");
                         self.connection_status = ConnectionStatus::Disconnected;
+                        self.subscriptions.session_ended();
                         break;
                     }
                     // hard error — back off and retry
                     Err(e) => {
                         error!("[EventSub] loop error: {}", e);
                         self.connection_status = ConnectionStatus::Reconnecting;
+                        self.subscriptions.session_ended();
                         sleep(Duration::from_secs(15)).await;
                         // Reset URL to default on error
                         url = "wss://eventsub.wss.twitch.tv/ws".to_string();
@@ -262,7 +266,9 @@ impl TwitchEventSubPlatform {
                 .and_then(|v| v.as_str()) {
                 Some("session_welcome") => {
                     if let Some(id) = parsed.pointer("/payload/session/id").and_then(|v| v.as_str()) {
-                        if let Err(e) = self.subscribe_all_events(id).await {
+                        let cred = self.credentials.as_ref()
+                            .ok_or_else(|| Error::Auth("No credential in TwitchEventSubPlatform".into()))?;
+                        if let Err(e) = self.subscriptions.session_started(id, cred).await {
                             error!("subscribe failed: {e:?}");
                         }
                     }
@@ -276,13 +282,9 @@ impl TwitchEventSubPlatform {
                     return Ok(Some(url));
                 }
                 Some("notification") => {
-                    if let Some(payload) = parsed.get("payload") {
-                        if let Ok(env) = serde_json::from_value::<EventSubNotificationEnvelope>(payload.clone()) {
-                            if let Some(evt) = parse_twitch_notification(&env.subscription.sub_type, &env.event) {
-                                if let Some(bus) = &self.event_bus {
-                                    bus.publish(BotEvent::TwitchEventSub(evt)).await;
-                                }
-                            }
+                    if let Some(evt) = parsed.get("payload").and_then(parse_notification_payload) {
+                        if let Some(bus) = &self.event_bus {
+                            bus.publish(BotEvent::TwitchEventSub(evt)).await;
                         }
                     }
                 }
@@ -292,124 +294,6 @@ impl TwitchEventSubPlatform {
         }
         Ok(None)        // natural close
     }
-
-
-    /// Modify this function to add your new channel points event subscriptions.
-    async fn subscribe_all_events(&self, session_id: &str) -> Result<(), Error> {
-        let cred = match &self.credentials {
-            Some(c) => c,
-            None => return Err(Error::Auth("No credential in TwitchEventSubPlatform".into())),
-        };
-        let access_token = &cred.primary_token;
-        let client_id = match cred.additional_data.as_ref()
-            .and_then(|v| v.get("client_id"))
-            .and_then(|j| j.as_str())
-        {
-            Some(s) => s.to_string(),
-            None => cred.platform_id.clone().unwrap_or_default(), // fallback
-        };
-
-        let broadcaster_id = cred.platform_id.clone().unwrap_or_default();
-        if broadcaster_id.is_empty() {
-            return Err(Error::Auth("No broadcaster user_id in credential.platform_id!".into()));
-        }
-
-        let http = ReqwestClient::new();
-
-        // Existing events plus your new channel points events:
-        let events_to_subscribe = vec![
-            // existing examples:
-            ("channel.bits.use", "1",  json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.update",   "2",     json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.follow",   "2",     json!({
-                "broadcaster_user_id": broadcaster_id,
-                "moderator_user_id": broadcaster_id
-            })),
-            ("channel.ad_break.begin", "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.chat.notification", "1", json!({
-                "broadcaster_user_id": broadcaster_id,
-                "user_id": broadcaster_id
-            })),
-            ("channel.shared_chat.begin",   "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.shared_chat.update",  "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.shared_chat.end",     "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.subscribe", "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.subscription.end",  "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.subscription.gift", "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.subscription.message", "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.cheer",  "1",  json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.raid",   "1",  json!({ "to_broadcaster_user_id": broadcaster_id })),
-            ("channel.ban",    "1",  json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.unban",  "1",  json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.unban_request.create", "1", json!({
-                "broadcaster_user_id": broadcaster_id,
-                "moderator_user_id": broadcaster_id
-            })),
-            ("channel.unban_request.resolve", "1", json!({
-                "broadcaster_user_id": broadcaster_id,
-                "moderator_user_id": broadcaster_id
-            })),
-            ("channel.hype_train.begin",    "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.hype_train.progress", "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.hype_train.end",      "1", json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.shoutout.create", "1", json!({
-                "broadcaster_user_id": broadcaster_id,
-                "moderator_user_id": broadcaster_id
-            })),
-            ("channel.shoutout.receive", "1", json!({
-                "broadcaster_user_id": broadcaster_id,
-                "moderator_user_id": broadcaster_id
-            })),
-            ("channel.channel_points_automatic_reward_redemption.add", "2",
-             json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.channel_points_custom_reward.add", "1",
-             json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.channel_points_custom_reward.update", "1",
-             json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.channel_points_custom_reward.remove", "1",
-             json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.channel_points_custom_reward_redemption.add", "1",
-             json!({ "broadcaster_user_id": broadcaster_id })),
-            ("channel.channel_points_custom_reward_redemption.update", "1",
-             json!({ "broadcaster_user_id": broadcaster_id })),
-            ("stream.online", "1",
-            json!({"broadcaster_user_id": broadcaster_id })),
-            ("stream.offline", "1",
-            json!({ "broadcaster_user_id": broadcaster_id })),
-        ];
-
-        for (etype, version, condition) in events_to_subscribe {
-            let body = json!({
-                "type": etype,
-                "version": version,
-                "condition": condition,
-                "transport": {
-                    "method": "websocket",
-                    "session_id": session_id
-                }
-            });
-            debug!("Subscribing to {} v{} => {:?}", etype, version, body);
-
-            let resp = http
-                .post("https://api.twitch.tv/helix/eventsub/subscriptions")
-                .header("Client-Id", &client_id)
-                .header("Authorization", format!("Bearer {}", access_token))
-                .json(&body)
-                .send()
-                .await
-                .map_err(|e| Error::Platform(format!("Error posting subscribe for {etype}: {e}")))?;
-
-            let status = resp.status();
-            if !status.is_success() {
-                let text = resp.text().await.unwrap_or_default();
-                warn!("[TwitchEventSub] Could not subscribe to {} => HTTP {} => {}", etype, status, text);
-            } else {
-                debug!("[TwitchEventSub] subscribed to {} OK", etype);
-            }
-        }
-
-        Ok(())
-    }
 }
 
 #[async_trait]
@@ -0,0 +1,448 @@
+// File: maowbot-core/src/platforms/twitch_eventsub/simulate.rs
+//
+// Builds synthetic EventSub "notification" messages for testing. Each supported
+// subscription type has a realistic sample event; callers may override any
+// field with a JSON object, which is deep-merged into the sample. The result is
+// parsed by the same code the websocket read loop uses, so a simulated event
+// is indistinguishable from a live one once it reaches the event bus.
+
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::eventbus::TwitchEventSubData;
+use crate::Error;
+use super::events::parse_notification_payload;
+
+const BROADCASTER_ID: &str = "1000001";
+const BROADCASTER_LOGIN: &str = "maowbot_test";
+const BROADCASTER_NAME: &str = "MaowBot_Test";
+const VIEWER_ID: &str = "2000002";
+const VIEWER_LOGIN: &str = "test_viewer";
+const VIEWER_NAME: &str = "Test_Viewer";
+
+/// Every subscription type with a sample event, with its subscription version.
+pub const SIMULATED_EVENT_TYPES: &[(&str, &str)] = &[
+    ("stream.online", "1"),
+    ("stream.offline", "1"),
+    ("channel.update", "2"),
+    ("channel.follow", "2"),
+    ("channel.ad_break.begin", "1"),
+    ("channel.bits.use", "1"),
+    ("channel.cheer", "1"),
+    ("channel.raid", "1"),
+    ("channel.subscribe", "1"),
+    ("channel.subscription.end", "1"),
+    ("channel.subscription.gift", "1"),
+    ("channel.subscription.message", "1"),
+    ("channel.chat.notification", "1"),
+    ("channel.shared_chat.begin", "1"),
+    ("channel.shared_chat.update", "1"),
+    ("channel.shared_chat.end", "1"),
+    ("channel.ban", "1"),
+    ("channel.unban", "1"),
+    ("channel.unban_request.create", "1"),
+    ("channel.unban_request.resolve", "1"),
+    ("channel.hype_train.begin", "1"),
+    ("channel.hype_train.progress", "1"),
+    ("channel.hype_train.end", "1"),
+    ("channel.shoutout.create", "1"),
+    ("channel.shoutout.receive", "1"),
+    ("channel.channel_points_automatic_reward_redemption.add", "2"),
+    ("channel.channel_points_custom_reward.add", "1"),
+    ("channel.channel_points_custom_reward.update", "1"),
+    ("channel.channel_points_custom_reward.remove", "1"),
+    ("channel.channel_points_custom_reward_redemption.add", "1"),
+    ("channel.channel_points_custom_reward_redemption.update", "1"),
+];
+
+/// Short names accepted in place of the full subscription type.
+pub const EVENT_TYPE_SHORTCUTS: &[(&str, &str)] = &[
+    ("online", "stream.online"),
+    ("offline", "stream.offline"),
+    ("update", "channel.update"),
+    ("follow", "channel.follow"),
+    ("ad", "channel.ad_break.begin"),
+    ("bits", "channel.bits.use"),
+    ("cheer", "channel.cheer"),
+    ("raid", "channel.raid"),
+    ("sub", "channel.subscribe"),
+    ("resub", "channel.subscription.message"),
+    ("gift", "channel.subscription.gift"),
+    ("gift-subs", "channel.subscription.gift"),
+    ("ban", "channel.ban"),
+    ("unban", "channel.unban"),
+    ("hype-begin", "channel.hype_train.begin"),
+    ("hype-progress", "channel.hype_train.progress"),
+    ("hype-end", "channel.hype_train.end"),
+    ("shoutout", "channel.shoutout.create"),
+    ("redeem", "channel.channel_points_custom_reward_redemption.add"),
+    ("auto-redeem", "channel.channel_points_automatic_reward_redemption.add"),
+];
+
+/// Map a shortcut or full subscription type to the full type, if supported.
+pub fn resolve_event_type(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase();
+    EVENT_TYPE_SHORTCUTS
+        .iter()
+        .find(|(short, _)| *short == name)
+        .map(|(_, full)| *full)
+        .or_else(|| SIMULATED_EVENT_TYPES.iter().find(|(t, _)| *t == name).map(|(t, _)| *t))
+}
+
+fn broadcaster() -> Value {
+    json!({
+        "broadcaster_user_id": BROADCASTER_ID,
+        "broadcaster_user_login": BROADCASTER_LOGIN,
+        "broadcaster_user_name": BROADCASTER_NAME,
+    })
+}
+
+fn viewer() -> Value {
+    json!({
+        "user_id": VIEWER_ID,
+        "user_login": VIEWER_LOGIN,
+        "user_name": VIEWER_NAME,
+    })
+}
+
+fn moderator() -> Value {
+    json!({
+        "moderator_user_id": BROADCASTER_ID,
+        "moderator_user_login": BROADCASTER_LOGIN,
+        "moderator_user_name": BROADCASTER_NAME,
+    })
+}
+
+fn contribution() -> Value {
+    json!({ "user_id": VIEWER_ID, "user_login": VIEWER_LOGIN, "user_name": VIEWER_NAME, "type": "bits", "total": 500 })
+}
+
+fn custom_reward() -> Value {
+    json!({
+        "id": Uuid::new_v4().to_string(),
+        "is_enabled": true,
+        "is_paused": false,
+        "is_in_stock": true,
+        "title": "Hydrate",
+        "cost": 100,
+        "prompt": "Drink some water",
+        "is_user_input_required": false,
+        "should_redemptions_skip_request_queue": false,
+        "cooldown_expires_at": null,
+        "redemptions_redeemed_current_stream": null,
+        "max_per_stream": { "is_enabled": false, "value": 0 },
+        "max_per_user_per_stream": { "is_enabled": false, "value": 0 },
+        "global_cooldown": { "is_enabled": false, "seconds": 0 },
+        "background_color": "#9147FF",
+    })
+}
+
+/// Sample event body for a full subscription type.
+pub fn sample_event(sub_type: &str) -> Option<Value> {
+    let now = Utc::now();
+    let ts = |offset: Duration| (now + offset).to_rfc3339();
+
+    let specific = match sub_type {
+        "stream.online" => json!({ "id": "9001", "type": "live", "started_at": ts(Duration::zero()) }),
+        "stream.offline" => json!({}),
+        "channel.update" => json!({
+            "title": "Simulated stream title",
+            "language": "en",
+            "category_id": "509658",
+            "category_name": "Just Chatting",
+            "content_classification_labels": [],
+        }),
+        "channel.follow" => merge_all(&[viewer(), json!({ "followed_at": ts(Duration::zero()) })]),
+        "channel.ad_break.begin" => json!({
+            "duration_seconds": "60",
+            "started_at": ts(Duration::zero()),
+            "is_automatic": "false",
+            "requester_user_id": BROADCASTER_ID,
+            "requester_user_login": BROADCASTER_LOGIN,
+            "requester_user_name": BROADCASTER_NAME,
+        }),
+        "channel.bits.use" => merge_all(&[viewer(), json!({
+            "bits": 100,
+            "type": "cheer",
+            "power_up": null,
+            "message": {
+                "text": "Cheer100 simulated cheer",
+                "fragments": [
+                    { "type": "cheermote", "text": "Cheer100", "cheermote": { "prefix": "cheer", "bits": 100, "tier": 100 } },
+                    { "type": "text", "text": " simulated cheer" },
+                ],
+            },
+        })]),
+        "channel.cheer" => merge_all(&[viewer(), json!({
+            "is_anonymous": false,
+            "message": "Cheer100 simulated cheer",
+            "bits": 100,
+        })]),
+        "channel.raid" => json!({
+            "from_broadcaster_user_id": VIEWER_ID,
+            "from_broadcaster_user_login": VIEWER_LOGIN,
+            "from_broadcaster_user_name": VIEWER_NAME,
+            "to_broadcaster_user_id": BROADCASTER_ID,
+            "to_broadcaster_user_login": BROADCASTER_LOGIN,
+            "to_broadcaster_user_name": BROADCASTER_NAME,
+            "viewers": 42,
+        }),
+        "channel.subscribe" | "channel.subscription.end" => {
+            merge_all(&[viewer(), json!({ "tier": "1000", "is_gift": false })])
+        }
+        "channel.subscription.gift" => merge_all(&[viewer(), json!({
+            "total": 5,
+            "tier": "1000",
+            "cumulative_total": 25,
+            "is_anonymous": false,
+        })]),
+        "channel.subscription.message" => merge_all(&[viewer(), json!({
+            "tier": "1000",
+            "message": { "text": "Simulated resub message", "emotes": [] },
+            "cumulative_months": 12,
+            "streak_months": 3,
+            "duration_months": 1,
+        })]),
+        "channel.chat.notification" => json!({
+            "chatter_user_id": VIEWER_ID,
+            "chatter_user_login": VIEWER_LOGIN,
+            "chatter_user_name": VIEWER_NAME,
+            "chatter_is_anonymous": false,
+            "color": "#9147FF",
+            "badges": [],
+            "system_message": "Test_Viewer subscribed at Tier 1.",
+            "message_id": Uuid::new_v4().to_string(),
+            "message": { "text": "", "fragments": [] },
+            "notice_type": "sub",
+            "sub": { "sub_tier": "1000", "is_prime": false, "duration_months": 1 },
+        }),
+        "channel.shared_chat.begin" | "channel.shared_chat.update" => json!({
+            "session_id": Uuid::new_v4().to_string(),
+            "host_broadcaster_user_id": BROADCASTER_ID,
+            "host_broadcaster_user_login": BROADCASTER_LOGIN,
+            "host_broadcaster_user_name": BROADCASTER_NAME,
+            "participants": [
+                { "broadcaster_user_id": BROADCASTER_ID, "broadcaster_user_login": BROADCASTER_LOGIN, "broadcaster_user_name": BROADCASTER_NAME },
+                { "broadcaster_user_id": VIEWER_ID, "broadcaster_user_login": VIEWER_LOGIN, "broadcaster_user_name": VIEWER_NAME },
+            ],
+        }),
+        "channel.shared_chat.end" => json!({
+            "session_id": Uuid::new_v4().to_string(),
+            "host_broadcaster_user_id": BROADCASTER_ID,
+            "host_broadcaster_user_login": BROADCASTER_LOGIN,
+            "host_broadcaster_user_name": BROADCASTER_NAME,
+        }),
+        "channel.ban" => merge_all(&[viewer(), moderator(), json!({
+            "reason": "Simulated ban",
+            "banned_at": ts(Duration::zero()),
+            "ends_at": ts(Duration::minutes(10)),
+            "is_permanent": false,
+        })]),
+        "channel.unban" => merge_all(&[viewer(), moderator()]),
+        "channel.unban_request.create" => merge_all(&[viewer(), json!({
+            "id": Uuid::new_v4().to_string(),
+            "text": "Please unban me",
+            "created_at": ts(Duration::zero()),
+        })]),
+        "channel.unban_request.resolve" => merge_all(&[viewer(), moderator(), json!({
+            "id": Uuid::new_v4().to_string(),
+            "resolution_text": "Simulated resolution",
+            "status": "approved",
+        })]),
+        "channel.hype_train.begin" | "channel.hype_train.progress" => json!({
+            "id": Uuid::new_v4().to_string(),
+            "total": 1500,
+            "progress": 500,
+            "goal": 2000,
+            "top_contributions": [contribution()],
+            "last_contribution": contribution(),
+            "level": 2,
+            "started_at": ts(Duration::minutes(-2)),
+            "expires_at": ts(Duration::minutes(3)),
+            "is_golden_kappa_train": false,
+        }),
+        "channel.hype_train.end" => json!({
+            "id": Uuid::new_v4().to_string(),
+            "level": 3,
+            "total": 4200,
+            "top_contributions": [contribution()],
+            "started_at": ts(Duration::minutes(-10)),
+            "ended_at": ts(Duration::zero()),
+            "cooldown_ends_at": ts(Duration::hours(1)),
+            "is_golden_kappa_train": false,
+        }),
+        "channel.shoutout.create" => merge_all(&[moderator(), json!({
+            "to_broadcaster_user_id": VIEWER_ID,
+            "to_broadcaster_user_login": VIEWER_LOGIN,
+            "to_broadcaster_user_name": VIEWER_NAME,
+            "started_at": ts(Duration::zero()),
+            "viewer_count": 42,
+            "cooldown_ends_at": ts(Duration::minutes(2)),
+            "target_cooldown_ends_at": ts(Duration::hours(1)),
+        })]),
+        "channel.shoutout.receive" => json!({
+            "from_broadcaster_user_id": VIEWER_ID,
+            "from_broadcaster_user_login": VIEWER_LOGIN,
+            "from_broadcaster_user_name": VIEWER_NAME,
+            "viewer_count": 42,
+            "started_at": ts(Duration::zero()),
+        }),
+        "channel.channel_points_automatic_reward_redemption.add" => merge_all(&[viewer(), json!({
+            "id": Uuid::new_v4().to_string(),
+            "reward": { "type": "send_highlighted_message", "channel_points": 100 },
+            "message": { "text": "Simulated highlighted message", "fragments": [] },
+            "redeemed_at": ts(Duration::zero()),
+        })]),
+        "channel.channel_points_custom_reward.add"
+        | "channel.channel_points_custom_reward.update"
+        | "channel.channel_points_custom_reward.remove" => custom_reward(),
+        "channel.channel_points_custom_reward_redemption.add"
+        | "channel.channel_points_custom_reward_redemption.update" => merge_all(&[viewer(), json!({
+            "id": Uuid::new_v4().to_string(),
+            "user_input": "",
+            "status": if sub_type.ends_with(".add") { "unfulfilled" } else { "fulfilled" },
+            "reward": {
+                "id": Uuid::new_v4().to_string(),
+                "title": "Hydrate",
+                "cost": 100,
+                "prompt": "Drink some water",
+            },
+            "redeemed_at": ts(Duration::zero()),
+        })]),
+        _ => return None,
+    };
+
+    Some(merge_all(&[broadcaster(), specific]))
+}
+
+fn merge_all(parts: &[Value]) -> Value {
+    let mut out = json!({});
+    for part in parts {
+        merge_json(&mut out, part);
+    }
+    out
+}
+
+/// Deep-merge `overrides` into `base`: objects merge key by key, anything else replaces.
+pub fn merge_json(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Object(base_map), Value::Object(override_map)) => {
+            for (key, value) in override_map {
+                match base_map.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+/// Wrap an event body in the full websocket message Twitch would send.
+pub fn notification_message(sub_type: &str, version: &str, event: Value) -> Value {
+    let now = Utc::now().to_rfc3339();
+    json!({
+        "metadata": {
+            "message_id": Uuid::new_v4().to_string(),
+            "message_type": "notification",
+            "message_timestamp": now,
+            "subscription_type": sub_type,
+            "subscription_version": version,
+        },
+        "payload": {
+            "subscription": {
+                "id": Uuid::new_v4().to_string(),
+                "type": sub_type,
+                "version": version,
+                "status": "enabled",
+                "cost": 0,
+                "condition": { "broadcaster_user_id": BROADCASTER_ID },
+                "transport": { "method": "websocket", "session_id": "simulated" },
+                "created_at": now,
+            },
+            "event": event,
+        }
+    })
+}
+
+/// A simulated notification: the full message that was built and the parsed event.
+#[derive(Debug, Clone)]
+pub struct SimulatedNotification {
+    pub sub_type: &'static str,
+    pub message: Value,
+    pub event: TwitchEventSubData,
+}
+
+/// Build and parse a notification for `name` (shortcut or full type) with `overrides`
+/// merged into the sample event.
+pub fn simulate_notification(name: &str, overrides: Option<&Value>) -> Result<SimulatedNotification, Error> {
+    let sub_type = resolve_event_type(name)
+        .ok_or_else(|| Error::Platform(format!("Unsupported EventSub type '{}'", name)))?;
+    let version = SIMULATED_EVENT_TYPES
+        .iter()
+        .find(|(t, _)| *t == sub_type)
+        .map(|(_, v)| *v)
+        .unwrap_or("1");
+
+    let mut event = sample_event(sub_type)
+        .ok_or_else(|| Error::Platform(format!("No sample event for '{}'", sub_type)))?;
+    if let Some(overrides) = overrides {
+        if !overrides.is_object() {
+            return Err(Error::Platform("Field overrides must be a JSON object".into()));
+        }
+        merge_json(&mut event, overrides);
+    }
+
+    let message = notification_message(sub_type, version, event);
+    let parsed = message
+        .get("payload")
+        .and_then(parse_notification_payload)
+        .ok_or_else(|| Error::Platform(format!(
+            "Event does not parse as '{}' - check the override field names and types", sub_type
+        )))?;
+
+    Ok(SimulatedNotification { sub_type, message, event: parsed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_sample_parses() {
+        for (sub_type, _) in SIMULATED_EVENT_TYPES {
+            assert!(
+                simulate_notification(sub_type, None).is_ok(),
+                "sample for {} does not parse",
+                sub_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_overrides_are_applied() {
+        let sim = simulate_notification("raid", Some(&json!({ "viewers": 500 }))).unwrap();
+        match sim.event {
+            TwitchEventSubData::ChannelRaid(raid) => assert_eq!(raid.viewers, 500),
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let sim = simulate_notification("channel.hype_train.progress", Some(&json!({ "last_contribution": { "total": 9 } }))).unwrap();
+        match sim.event {
+            TwitchEventSubData::ChannelHypeTrainProgress(train) => {
+                assert_eq!(train.last_contribution.total, 9);
+                assert_eq!(train.last_contribution.user_login, VIEWER_LOGIN);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_bad_overrides_are_rejected() {
+        assert!(simulate_notification("raid", Some(&json!({ "viewers": "lots" }))).is_err());
+        assert!(simulate_notification("nonsense", None).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+// File: maowbot-core/src/platforms/twitch_eventsub/subscriptions.rs
+//
+// Keeps the EventSub subscriptions of the websocket session in line with what
+// the bot needs: creates the missing ones, leaves the ones that exist, and
+// deletes websocket subscriptions that are broken, left over from an earlier
+// session, or for a channel other than the broadcaster's. Creation stops once
+// Twitch's `max_total_cost` is used up. Webhook and conduit subscriptions made
+// with the same client ID are left alone.
+
+use std::sync::Mutex;
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use maowbot_common::models::platform::PlatformCredential;
+
+use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::platforms::twitch::requests::eventsub::{CreatedSubscription, EventSubSubscription};
+use crate::platforms::twitch::requests::token::ensure_valid_token;
+use crate::Error;
+
+/// A subscription the bot wants for the broadcaster's channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequiredSubscription {
+    pub sub_type: &'static str,
+    pub version: &'static str,
+    pub condition: Value,
+}
+
+impl RequiredSubscription {
+    fn new(sub_type: &'static str, version: &'static str, condition: Value) -> Self {
+        Self { sub_type, version, condition }
+    }
+
+    /// Twitch echoes every condition field back, so only ours are compared.
+    fn matches(&self, existing: &EventSubSubscription) -> bool {
+        existing.sub_type == self.sub_type
+            && existing.version == self.version
+            && self.condition.as_object().is_some_and(|wanted| {
+                wanted.iter().all(|(key, value)| existing.condition.get(key) == Some(value))
+            })
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} v{}", self.sub_type, self.version)
+    }
+}
+
+/// Every subscription the EventSub runtime makes. Add new event types here.
+pub fn required_subscriptions(broadcaster_id: &str) -> Vec<RequiredSubscription> {
+    let broadcaster = json!({ "broadcaster_user_id": broadcaster_id });
+    let as_moderator = json!({ "broadcaster_user_id": broadcaster_id, "moderator_user_id": broadcaster_id });
+    let req = RequiredSubscription::new;
+    vec![
+        req("channel.bits.use", "1", broadcaster.clone()),
+        req("channel.update", "2", broadcaster.clone()),
+        req("channel.follow", "2", as_moderator.clone()),
+        req("channel.ad_break.begin", "1", broadcaster.clone()),
+        req("channel.chat.notification", "1", json!({ "broadcaster_user_id": broadcaster_id, "user_id": broadcaster_id })),
+        req("channel.shared_chat.begin", "1", broadcaster.clone()),
+        req("channel.shared_chat.update", "1", broadcaster.clone()),
+        req("channel.shared_chat.end", "1", broadcaster.clone()),
+        req("channel.subscribe", "1", broadcaster.clone()),
+        req("channel.subscription.end", "1", broadcaster.clone()),
+        req("channel.subscription.gift", "1", broadcaster.clone()),
+        req("channel.subscription.message", "1", broadcaster.clone()),
+        req("channel.cheer", "1", broadcaster.clone()),
+        req("channel.raid", "1", json!({ "to_broadcaster_user_id": broadcaster_id })),
+        req("channel.ban", "1", broadcaster.clone()),
+        req("channel.unban", "1", broadcaster.clone()),
+        req("channel.unban_request.create", "1", as_moderator.clone()),
+        req("channel.unban_request.resolve", "1", as_moderator.clone()),
+        req("channel.hype_train.begin", "1", broadcaster.clone()),
+        req("channel.hype_train.progress", "1", broadcaster.clone()),
+        req("channel.hype_train.end", "1", broadcaster.clone()),
+        req("channel.shoutout.create", "1", as_moderator.clone()),
+        req("channel.shoutout.receive", "1", as_moderator),
+        req("channel.channel_points_automatic_reward_redemption.add", "2", broadcaster.clone()),
+        req("channel.channel_points_custom_reward.add", "1", broadcaster.clone()),
+        req("channel.channel_points_custom_reward.update", "1", broadcaster.clone()),
+        req("channel.channel_points_custom_reward.remove", "1", broadcaster.clone()),
+        req("channel.channel_points_custom_reward_redemption.add", "1", broadcaster.clone()),
+        req("channel.channel_points_custom_reward_redemption.update", "1", broadcaster.clone()),
+        req("stream.online", "1", broadcaster.clone()),
+        req("stream.offline", "1", broadcaster),
+        // Only granted with user:read:whispers or user:manage:whispers
+        req("user.whisper.message", "1", json!({ "user_id": broadcaster_id })),
+    ]
+}
+
+/// Why an existing subscription would be deleted.
+fn stale_reason(
+    existing: &EventSubSubscription,
+    required: &[RequiredSubscription],
+    session_id: &str,
+    broadcaster_id: &str,
+) -> Option<String> {
+    if existing.status != "enabled" {
+        return Some(existing.status.clone());
+    }
+    if existing.transport.session_id.as_deref() != Some(session_id) {
+        return Some("from an earlier session".into());
+    }
+    let other_channel = existing.condition.as_object().is_some_and(|condition| {
+        condition.iter().any(|(key, value)| {
+            key.ends_with("user_id") && value.as_str().is_some_and(|id| !id.is_empty() && id != broadcaster_id)
+        })
+    });
+    if other_channel {
+        return Some("channel is no longer configured".into());
+    }
+    if !required.iter().any(|r| r.matches(existing)) {
+        return Some("no longer needed".into());
+    }
+    None
+}
+
+/// What a sync would change.
+#[derive(Debug, Default)]
+pub struct SyncPlan {
+    pub create: Vec<RequiredSubscription>,
+    /// (subscription, reason)
+    pub prune: Vec<(EventSubSubscription, String)>,
+    pub keep: Vec<EventSubSubscription>,
+}
+
+/// Compares the websocket subscriptions that exist with the required ones.
+pub fn plan_sync(
+    existing: &[EventSubSubscription],
+    required: &[RequiredSubscription],
+    session_id: &str,
+    broadcaster_id: &str,
+) -> SyncPlan {
+    let mut plan = SyncPlan::default();
+    for sub in existing.iter().filter(|s| s.transport.method == "websocket") {
+        let reason = stale_reason(sub, required, session_id, broadcaster_id).or_else(|| {
+            plan.keep.iter()
+                .any(|kept| kept.sub_type == sub.sub_type && kept.version == sub.version && kept.condition == sub.condition)
+                .then(|| "duplicate".to_string())
+        });
+        match reason {
+            Some(reason) => plan.prune.push((sub.clone(), reason)),
+            None => plan.keep.push(sub.clone()),
+        }
+    }
+    plan.create = required.iter()
+        .filter(|r| !plan.keep.iter().any(|kept| r.matches(kept)))
+        .cloned()
+        .collect();
+    plan
+}
+
+/// The subscriptions as Twitch reports them, with what a repair would do.
+#[derive(Debug, Default)]
+pub struct EventSubOverview {
+    pub session_id: Option<String>,
+    pub subscriptions: Vec<EventSubSubscription>,
+    pub total_cost: i64,
+    pub max_total_cost: i64,
+    /// Required subscriptions that don't exist
+    pub missing: Vec<String>,
+    /// (subscription id, reason) of the ones a repair would delete
+    pub stale: Vec<(String, String)>,
+}
+
+/// What a sync did.
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub created: Vec<String>,
+    pub unchanged: usize,
+    /// (subscription, reason)
+    pub pruned: Vec<(EventSubSubscription, String)>,
+    /// (subscription, error)
+    pub failed: Vec<(String, String)>,
+    /// Not attempted because `max_total_cost` was reached
+    pub over_budget: Vec<String>,
+    pub total_cost: i64,
+    pub max_total_cost: i64,
+}
+
+struct EventSubSession {
+    session_id: String,
+    credential: PlatformCredential,
+}
+
+/// The EventSub runtime reports its websocket session here, so subscriptions
+/// can be listed and repaired while it runs.
+#[derive(Default)]
+pub struct EventSubManager {
+    session: Mutex<Option<EventSubSession>>,
+}
+
+impl EventSubManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called on every `session_welcome`; brings the new session's
+    /// subscriptions up to date.
+    pub async fn session_started(&self, session_id: &str, credential: &PlatformCredential) -> Result<SyncReport, Error> {
+        *self.session.lock().unwrap() = Some(EventSubSession {
+            session_id: session_id.to_string(),
+            credential: credential.clone(),
+        });
+        let report = self.repair().await?;
+        info!(
+            "[EventSub] subscriptions synced: {} created, {} unchanged, {} pruned, {} failed, {} over budget (cost {}/{})",
+            report.created.len(), report.unchanged, report.pruned.len(), report.failed.len(),
+            report.over_budget.len(), report.total_cost, report.max_total_cost
+        );
+        Ok(report)
+    }
+
+    pub fn session_ended(&self) {
+        *self.session.lock().unwrap() = None;
+    }
+
+    pub fn session_id(&self) -> Option<String> {
+        self.session.lock().unwrap().as_ref().map(|s| s.session_id.clone())
+    }
+
+    /// A Helix client for the session's credential, refreshed if close to
+    /// expiring, and the broadcaster id.
+    async fn helix(&self) -> Result<(TwitchHelixClient, String, String), Error> {
+        let (session_id, credential) = self.session.lock().unwrap().as_ref()
+            .map(|s| (s.session_id.clone(), s.credential.clone()))
+            .ok_or_else(|| Error::NotFound(
+                "EventSub is not connected; start it with `connection start twitch-eventsub <account>`".into()
+            ))?;
+        let client_id = credential.additional_data.as_ref()
+            .and_then(|d| d.get("client_id"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or_else(|| std::env::var("TWITCH_CLIENT_ID").ok())
+            .unwrap_or_default();
+        let client_secret = std::env::var("TWITCH_CLIENT_SECRET").ok();
+        let credential = ensure_valid_token(&credential, &client_id, client_secret.as_deref(), 600).await?;
+        let broadcaster_id = credential.platform_id.clone()
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| Error::Auth("No broadcaster user_id in credential.platform_id!".into()))?;
+        Ok((TwitchHelixClient::new(&credential.primary_token, &client_id), session_id, broadcaster_id))
+    }
+
+    pub async fn overview(&self) -> Result<EventSubOverview, Error> {
+        let (helix, session_id, broadcaster_id) = self.helix().await?;
+        let listed = helix.list_eventsub_subscriptions().await?;
+        let plan = plan_sync(&listed.subscriptions, &required_subscriptions(&broadcaster_id), &session_id, &broadcaster_id);
+        Ok(EventSubOverview {
+            session_id: Some(session_id),
+            subscriptions: listed.subscriptions,
+            total_cost: listed.total_cost,
+            max_total_cost: listed.max_total_cost,
+            missing: plan.create.iter().map(RequiredSubscription::label).collect(),
+            stale: plan.prune.into_iter().map(|(sub, reason)| (sub.id, reason)).collect(),
+        })
+    }
+
+    /// Deletes stale subscriptions and creates missing ones. Safe to repeat:
+    /// what already exists is left as is.
+    pub async fn repair(&self) -> Result<SyncReport, Error> {
+        let (helix, session_id, broadcaster_id) = self.helix().await?;
+        let listed = helix.list_eventsub_subscriptions().await?;
+        let plan = plan_sync(&listed.subscriptions, &required_subscriptions(&broadcaster_id), &session_id, &broadcaster_id);
+
+        let mut report = SyncReport {
+            unchanged: plan.keep.len(),
+            total_cost: listed.total_cost,
+            max_total_cost: listed.max_total_cost,
+            ..Default::default()
+        };
+
+        // Pruning first frees budget for the creates
+        for (sub, reason) in plan.prune {
+            match helix.delete_eventsub_subscription(&sub.id).await {
+                Ok(()) => {
+                    debug!("[EventSub] deleted {} v{} ({})", sub.sub_type, sub.version, reason);
+                    if sub.status == "enabled" {
+                        report.total_cost -= sub.cost;
+                    }
+                    report.pruned.push((sub, reason));
+                }
+                Err(e) => report.failed.push((format!("delete {} v{}", sub.sub_type, sub.version), e.to_string())),
+            }
+        }
+
+        for required in plan.create {
+            let label = required.label();
+            if report.max_total_cost > 0 && report.total_cost >= report.max_total_cost {
+                report.over_budget.push(label);
+                continue;
+            }
+            match helix.create_eventsub_subscription(required.sub_type, required.version, &required.condition, &session_id).await {
+                Ok(CreatedSubscription::Created { total_cost, max_total_cost, .. }) => {
+                    debug!("[EventSub] subscribed to {}", label);
+                    report.total_cost = total_cost;
+                    report.max_total_cost = max_total_cost;
+                    report.created.push(label);
+                }
+                Ok(CreatedSubscription::AlreadyExists) => report.unchanged += 1,
+                Err(e) => {
+                    warn!("[EventSub] could not subscribe to {}: {}", label, e);
+                    report.failed.push((label, e.to_string()));
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::twitch::requests::eventsub::EventSubTransport;
+
+    fn sub(id: &str, sub_type: &str, condition: Value, session: &str, status: &str) -> EventSubSubscription {
+        EventSubSubscription {
+            id: id.into(),
+            sub_type: sub_type.into(),
+            version: "1".into(),
+            status: status.into(),
+            cost: 0,
+            condition,
+            transport: EventSubTransport { method: "websocket".into(), session_id: Some(session.into()) },
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_keeps_matches_prunes_stale_and_creates_missing() {
+        let required = vec![
+            RequiredSubscription::new("stream.online", "1", json!({ "broadcaster_user_id": "42" })),
+            RequiredSubscription::new("channel.raid", "1", json!({ "to_broadcaster_user_id": "42" })),
+        ];
+        let existing = vec![
+            sub("a", "stream.online", json!({ "broadcaster_user_id": "42" }), "s2", "enabled"),
+            sub("b", "stream.online", json!({ "broadcaster_user_id": "42" }), "s2", "enabled"),
+            sub("c", "stream.online", json!({ "broadcaster_user_id": "42" }), "s1", "websocket_disconnected"),
+            sub("d", "stream.online", json!({ "broadcaster_user_id": "7" }), "s2", "enabled"),
+            sub("e", "channel.cheer", json!({ "broadcaster_user_id": "42" }), "s2", "enabled"),
+            sub("f", "channel.raid", json!({ "to_broadcaster_user_id": "42", "from_broadcaster_user_id": "" }), "s2", "authorization_revoked"),
+        ];
+
+        let plan = plan_sync(&existing, &required, "s2", "42");
+        assert_eq!(plan.keep.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["a"]);
+        let pruned: Vec<(&str, &str)> = plan.prune.iter().map(|(s, r)| (s.id.as_str(), r.as_str())).collect();
+        assert_eq!(pruned, vec![
+            ("b", "duplicate"),
+            ("c", "websocket_disconnected"),
+            ("d", "channel is no longer configured"),
+            ("e", "no longer needed"),
+            ("f", "authorization_revoked"),
+        ]);
+        assert_eq!(plan.create.iter().map(|r| r.sub_type).collect::<Vec<_>>(), vec!["channel.raid"]);
+
+        // Echoed empty condition fields still match
+        let echoed = sub("g", "channel.raid", json!({ "to_broadcaster_user_id": "42", "from_broadcaster_user_id": "" }), "s2", "enabled");
+        assert!(plan_sync(&[echoed], &required[1..], "s2", "42").create.is_empty());
+    }
+}
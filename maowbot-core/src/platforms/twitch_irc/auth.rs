@@ -10,28 +10,27 @@ use maowbot_common::traits::auth_traits::{AuthenticationPrompt, AuthenticationRe
 use maowbot_common::models::platform::{Platform, PlatformCredential};
 use maowbot_common::models::credential::CredentialType;
 use crate::Error;
+use crate::platforms::twitch::device_code::{self, PendingDeviceCode};
 
 #[derive(Deserialize)]
 struct TwitchTokenResponse {
     access_token: String,
     refresh_token: Option<String>,
     expires_in: u64,
-    scope: Option<Vec<String>>,
-    token_type: String,
 }
 
 /// Additional shape for /validate response
 #[derive(Deserialize)]
 struct TwitchValidateResponse {
-    client_id: String,
     login: String,
     user_id: String,
-    expires_in: u64,
 }
 
 /// A simple static for unique `state` each time.
 static IRC_STATE_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
+pub const IRC_SCOPES: &[&str] = &["chat:read", "chat:edit"];
+
 pub struct TwitchIrcAuthenticator {
     pub client_id: String,
     pub client_secret: Option<String>,
@@ -39,6 +38,7 @@ pub struct TwitchIrcAuthenticator {
     pub is_teammate: bool,
     pub is_bot: bool,
     pending_state: Option<String>,
+    pending_device: Option<PendingDeviceCode>,
 }
 
 impl TwitchIrcAuthenticator {
@@ -50,12 +50,12 @@ impl TwitchIrcAuthenticator {
             is_teammate: true,
             is_bot: true,
             pending_state: None,
+            pending_device: None,
         }
     }
 
     fn build_auth_url(&self, state: &str) -> String {
-        let scopes = vec!["chat:read", "chat:edit"];
-        let scope_str = scopes.join(" ");
+        let scope_str = IRC_SCOPES.join(" ");
         let redirect_uri = "http://localhost:9876/callback";
         format!(
             "https://id.twitch.tv/oauth2/authorize?response_type=code&client_id={}&redirect_uri={}&scope={}&state={}",
@@ -91,6 +91,37 @@ impl TwitchIrcAuthenticator {
         debug!("TwitchIrcAuthenticator /validate => login={}, user_id={}", val.login, val.user_id);
         Ok((val.login, val.user_id))
     }
+
+    /// Builds a new IRC credential from a token response (authorization code or device grant).
+    async fn credential_from_token(&self, resp: TwitchTokenResponse) -> Result<PlatformCredential, Error> {
+        let now = Utc::now();
+        let expires_at = Some(now + chrono::Duration::seconds(resp.expires_in as i64));
+
+        // The raw token is used with "oauth:XXXX" for PASS. But let's also fetch user login + ID
+        let raw_token = resp.access_token.clone();
+        let (login, user_id) = self.fetch_user_login_and_id(&raw_token).await?;
+
+        let with_oauth_prefix = format!("oauth:{}", raw_token);
+        let credential = PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::TwitchIRC,
+            platform_id: Some(user_id),
+            credential_type: CredentialType::OAuth2,
+            user_id: Uuid::new_v4(),   // will be overwritten later if we do "complete_auth_flow_for_user"
+            user_name: login,          // from /validate call
+            primary_token: with_oauth_prefix,
+            refresh_token: resp.refresh_token,
+            additional_data: None,
+            expires_at,
+            created_at: now,
+            updated_at: now,
+            is_broadcaster: self.is_broadcaster,
+            is_teammate: self.is_teammate,
+            is_bot: self.is_bot,
+        };
+
+        Ok(credential)
+    }
 }
 
 #[async_trait]
@@ -135,34 +166,25 @@ impl PlatformAuthenticator for TwitchIrcAuthenticator {
             .await
             .map_err(|e| Error::Auth(format!("Parse error on token JSON: {e}")))?;
 
-        let now = Utc::now();
-        let expires_at = Some(now + chrono::Duration::seconds(resp.expires_in as i64));
+        self.pending_state = None;
+        self.credential_from_token(resp).await
+    }
 
-        // The raw token is used with "oauth:XXXX" for PASS. But let's also fetch user login + ID
-        let raw_token = resp.access_token.clone();
-        let (login, user_id) = self.fetch_user_login_and_id(&raw_token).await?;
+    async fn start_device_authentication(&mut self) -> Result<AuthenticationPrompt, Error> {
+        let (prompt, pending) = device_code::request_device_code(&self.client_id, IRC_SCOPES).await?;
+        self.pending_device = Some(pending);
+        Ok(prompt)
+    }
 
-        let with_oauth_prefix = format!("oauth:{}", raw_token);
-        let credential = PlatformCredential {
-            credential_id: Uuid::new_v4(),
-            platform: Platform::TwitchIRC,
-            platform_id: Some(user_id),
-            credential_type: CredentialType::OAuth2,
-            user_id: Uuid::new_v4(),   // will be overwritten later if we do "complete_auth_flow_for_user"
-            user_name: login,          // from /validate call
-            primary_token: with_oauth_prefix,
-            refresh_token: resp.refresh_token,
-            additional_data: None,
-            expires_at,
-            created_at: now,
-            updated_at: now,
-            is_broadcaster: self.is_broadcaster,
-            is_teammate: self.is_teammate,
-            is_bot: self.is_bot,
+    async fn poll_device_authentication(&mut self) -> Result<Option<PlatformCredential>, Error> {
+        let Some(pending) = self.pending_device.as_mut() else {
+            return Err(Error::Auth("No device-code login in progress".into()));
         };
-
-        self.pending_state = None;
-        Ok(credential)
+        let Some(resp) = device_code::poll_device_token::<TwitchTokenResponse>(&self.client_id, pending).await? else {
+            return Ok(None);
+        };
+        self.pending_device = None;
+        self.credential_from_token(resp).await.map(Some)
     }
 
     async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
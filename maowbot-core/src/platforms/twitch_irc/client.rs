@@ -1,5 +1,4 @@
 use std::io;
-use std::sync::Arc;
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter, split};
 use tokio::net::TcpStream;
@@ -10,6 +9,8 @@ use tokio_native_tls::native_tls;
 use tokio_native_tls::TlsConnector;
 use tracing::{info, error, debug, trace};
 
+use super::tags::{unescape_tag_value, ReplyParent};
+
 /// Minimal representation of a parsed IRC message from Twitch.
 #[derive(Debug, Clone)]
 pub struct ParsedTwitchMsg {
@@ -28,7 +29,7 @@ fn extract_tag_value(tag_str: &str, key: &str) -> Option<String> {
         let left = parts.next().unwrap_or("");
         let right = parts.next().unwrap_or("");
         if left == key {
-            return Some(right.to_string());
+            return Some(unescape_tag_value(right));
         }
     }
     None
@@ -65,6 +66,18 @@ fn parse_twitch_roles(tags: &str) -> Vec<String> {
     roles
 }
 
+/// The `reply-parent-*` tags, when the message is a reply.
+fn parse_reply_parent(tags: &str) -> Option<ReplyParent> {
+    let message_id = extract_tag_value(tags, "reply-parent-msg-id").filter(|id| !id.is_empty())?;
+    Some(ReplyParent {
+        message_id,
+        user_id: extract_tag_value(tags, "reply-parent-user-id").unwrap_or_default(),
+        user_login: extract_tag_value(tags, "reply-parent-user-login").unwrap_or_default(),
+        display_name: extract_tag_value(tags, "reply-parent-display-name").unwrap_or_default(),
+        text: extract_tag_value(tags, "reply-parent-msg-body").unwrap_or_default(),
+    })
+}
+
 impl ParsedTwitchMsg {
     pub fn parse_irc_line(line: &str) -> Self {
         let mut rest = line.trim();
@@ -151,6 +164,20 @@ pub struct IrcIncomingEvent {
     pub raw_line: String,
     pub command: String,
     pub roles: Vec<String>,
+    /// The `id` tag of a PRIVMSG, needed to delete it
+    pub message_id: Option<String>,
+    /// The raw `emotes` tag, e.g. "25:0-4,12-16/1902:6-10"
+    pub emotes: Option<String>,
+    /// The channel owner's user id (`room-id` tag)
+    pub room_id: Option<String>,
+    /// The raw `badges` tag, e.g. "broadcaster/1,subscriber/12"
+    pub badges: Option<String>,
+    /// The chatter's name color, e.g. "#FF4500"; None if they never picked one
+    pub color: Option<String>,
+    /// The message this one replies to
+    pub reply_parent: Option<ReplyParent>,
+    /// The `msg-id` tag, e.g. "highlighted-message" for a channel point highlight
+    pub msg_id: Option<String>,
 }
 
 pub struct TwitchIrcClient {
@@ -164,15 +191,15 @@ pub struct TwitchIrcClient {
 impl TwitchIrcClient {
     pub async fn connect(username: &str, oauth_token: &str) -> io::Result<Self> {
         let tcp = TcpStream::connect(("irc.chat.twitch.tv", 6697)).await
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("TCP connect error: {e}")))?;
+            .map_err(|e| io::Error::other(format!("TCP connect error: {e}")))?;
 
         let native_connector = native_tls::TlsConnector::new()
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("TLSConnector::new() => {e}")))?;
+            .map_err(|e| io::Error::other(format!("TLSConnector::new() => {e}")))?;
         let connector = TlsConnector::from(native_connector);
 
         let domain = "irc.chat.twitch.tv";
         let tls_stream = connector.connect(domain, tcp).await
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("TLS connect() => {e}")))?;
+            .map_err(|e| io::Error::other(format!("TLS connect() => {e}")))?;
 
         let (read_half, write_half) = split(tls_stream);
 
@@ -241,11 +268,18 @@ impl TwitchIrcClient {
                         raw_line: line.clone(),
                         command: command.clone(),
                         roles: vec![],
+                        message_id: None,
+                        emotes: None,
+                        room_id: None,
+                        badges: None,
+                        color: None,
+                        reply_parent: None,
+                        msg_id: None,
                     };
 
                     if command == "PRIVMSG" {
                         // Usually in parsed.params[0] is "#channel"
-                        if let Some(ch) = parsed.params.get(0) {
+                        if let Some(ch) = parsed.params.first() {
                             evt.channel = Some(ch.clone());
                         }
                         evt.text = parsed.trailing.clone();
@@ -259,6 +293,13 @@ impl TwitchIrcClient {
                                 evt.display_name = Some(dn);
                             }
                             evt.roles = parse_twitch_roles(tags);
+                            evt.message_id = extract_tag_value(tags, "id");
+                            evt.emotes = extract_tag_value(tags, "emotes").filter(|e| !e.is_empty());
+                            evt.room_id = extract_tag_value(tags, "room-id");
+                            evt.badges = extract_tag_value(tags, "badges").filter(|b| !b.is_empty());
+                            evt.color = extract_tag_value(tags, "color").filter(|c| !c.is_empty());
+                            evt.reply_parent = parse_reply_parent(tags);
+                            evt.msg_id = extract_tag_value(tags, "msg-id").filter(|m| !m.is_empty());
                         }
                         else if let Some(pref) = &parsed.prefix {
                             // fallback for username in prefix
@@ -270,7 +311,7 @@ impl TwitchIrcClient {
                     }
                     else if command == "JOIN" || command == "PART" {
                         // channel is in params[0], name is from prefix or display-name in tags
-                        if let Some(ch) = parsed.params.get(0) {
+                        if let Some(ch) = parsed.params.first() {
                             evt.channel = Some(ch.clone());
                         }
                         if let Some(tags) = &parsed.tags {
@@ -337,6 +378,12 @@ impl TwitchIrcClient {
         let _ = self.raw_outgoing.send(cmd);
     }
 
+    /// Sends `message` as a threaded reply to the message with id `parent_id`.
+    pub fn send_reply(&self, channel: &str, parent_id: &str, message: &str) {
+        let cmd = format!("@reply-parent-msg-id={} PRIVMSG {} :{}", parent_id, channel, message);
+        let _ = self.raw_outgoing.send(cmd);
+    }
+
     pub fn shutdown(self) {
         self.read_task.abort();
         self.write_task.abort();
@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod runtime;
+pub mod tags;
 mod client;
 
 pub use auth::TwitchIrcAuthenticator;
@@ -8,7 +8,8 @@ use crate::eventbus::EventBus;
 use maowbot_common::models::platform::PlatformCredential;
 use maowbot_common::traits::platform_traits::{ChatPlatform, ConnectionStatus, PlatformAuth, PlatformIntegration};
 
-use super::client::{TwitchIrcClient, IrcIncomingEvent};
+use super::client::TwitchIrcClient;
+use super::tags::ReplyParent;
 
 #[derive(Debug, Clone)]
 pub struct TwitchIrcMessageEvent {
@@ -19,6 +20,19 @@ pub struct TwitchIrcMessageEvent {
     pub display_name: String,
     pub text: String,
     pub roles: Vec<String>,
+    /// Twitch's id for the message; empty if the tags were missing
+    pub message_id: String,
+    /// The raw `emotes` tag; empty if the message has no Twitch emotes
+    pub emotes: String,
+    /// The channel owner's user id; empty if the tags were missing
+    pub room_id: String,
+    /// The raw `badges` tag; empty if the chatter has none
+    pub badges: String,
+    /// The chatter's name color; empty if they never picked one
+    pub color: String,
+    pub reply_parent: Option<ReplyParent>,
+    /// The `msg-id` tag (e.g. "highlighted-message"); empty for plain messages
+    pub msg_id: String,
 }
 
 pub struct TwitchIrcPlatform {
@@ -38,6 +52,12 @@ pub struct TwitchIrcPlatform {
     pub enable_incoming: bool,
 }
 
+impl Default for TwitchIrcPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TwitchIrcPlatform {
     pub fn new() -> Self {
         Self {
@@ -60,6 +80,16 @@ impl TwitchIrcPlatform {
         self.event_bus = Some(bus);
     }
 
+    /// Replies to the chat message with id `parent_id`.
+    pub async fn send_reply(&self, channel: &str, parent_id: &str, message: &str) -> Result<(), Error> {
+        if let Some(cli) = &self.client {
+            cli.send_reply(channel, parent_id, message);
+            Ok(())
+        } else {
+            Err(Error::Platform("No active Twitch IRC connection".into()))
+        }
+    }
+
     /// Helper to consume next message event if this platform is in "receive" mode
     pub async fn next_message_event(&mut self) -> Option<TwitchIrcMessageEvent> {
         if !self.enable_incoming {
@@ -168,7 +198,7 @@ impl PlatformIntegration for TwitchIrcPlatform {
                 .ok_or_else(|| Error::Platform("No incoming channel in TwitchIrcClient".into()))?;
 
             let tx_for_task = self.tx.as_ref().unwrap().clone();
-            let event_bus_for_task = self.event_bus.clone();
+            let _event_bus_for_task = self.event_bus.clone();
 
             let handle = tokio::spawn(async move {
                 while let Some(evt) = irc_incoming.recv().await {
@@ -187,6 +217,13 @@ impl PlatformIntegration for TwitchIrcPlatform {
                                 .unwrap_or_else(|| "<unknown>".into()),
                             text:  evt.text.clone().unwrap_or_default(),
                             roles: evt.roles.clone(),
+                            message_id: evt.message_id.clone().unwrap_or_default(),
+                            emotes: evt.emotes.clone().unwrap_or_default(),
+                            room_id: evt.room_id.clone().unwrap_or_default(),
+                            badges: evt.badges.clone().unwrap_or_default(),
+                            color: evt.color.clone().unwrap_or_default(),
+                            reply_parent: evt.reply_parent.clone(),
+                            msg_id: evt.msg_id.clone().unwrap_or_default(),
                         };
                         let _ = tx_for_task.send(msg_evt).await;
                         // (optional event-bus publish unchanged)
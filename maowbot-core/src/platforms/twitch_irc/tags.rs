@@ -0,0 +1,109 @@
+// File: src/platforms/twitch_irc/tags.rs
+//
+// The IRCv3 tags Twitch sends with each PRIVMSG that a chat view needs to
+// draw it like Twitch does: `badges=broadcaster/1,subscriber/12`,
+// `color=#FF4500`, `emotes=25:0-4,12-16/1902:6-10` and the
+// `reply-parent-*` tags of a threaded reply.
+
+/// One chat badge, e.g. `subscriber/12`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatBadge {
+    pub set_id: String,
+    pub version: String,
+}
+
+/// Where one Twitch emote sits in the message text. `start` and `end` are
+/// inclusive and count characters (code points), not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmoteRange {
+    pub emote_id: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The message a chat reply answers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplyParent {
+    pub message_id: String,
+    pub user_id: String,
+    pub user_login: String,
+    pub display_name: String,
+    pub text: String,
+}
+
+/// Undoes IRCv3 tag escaping (`\s` space, `\:` semicolon, `\\`, `\r`, `\n`).
+pub fn unescape_tag_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some(':') => out.push(';'),
+            Some('\\') => out.push('\\'),
+            Some('r') => out.push('\r'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Parses a `badges` (or `badge-info`) tag.
+pub fn parse_badges(tag: &str) -> Vec<ChatBadge> {
+    tag.split(',')
+        .filter_map(|part| part.split_once('/'))
+        .filter(|(set_id, _)| !set_id.is_empty())
+        .map(|(set_id, version)| ChatBadge {
+            set_id: set_id.to_string(),
+            version: version.to_string(),
+        })
+        .collect()
+}
+
+/// Parses an `emotes` tag into ranges ordered by position in the message.
+pub fn parse_emote_ranges(tag: &str) -> Vec<EmoteRange> {
+    let mut ranges: Vec<EmoteRange> = tag.split('/')
+        .filter_map(|emote| emote.split_once(':'))
+        .flat_map(|(emote_id, positions)| {
+            positions.split(',').filter_map(move |range| {
+                let (start, end) = range.split_once('-')?;
+                Some(EmoteRange {
+                    emote_id: emote_id.to_string(),
+                    start: start.parse().ok()?,
+                    end: end.parse().ok()?,
+                })
+            })
+        })
+        .filter(|r| r.start <= r.end)
+        .collect();
+    ranges.sort_by_key(|r| r.start);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_badges_and_emote_ranges() {
+        let badges = parse_badges("broadcaster/1,subscriber/12,");
+        assert_eq!(badges.len(), 2);
+        assert_eq!(badges[1], ChatBadge { set_id: "subscriber".into(), version: "12".into() });
+        assert!(parse_badges("").is_empty());
+
+        let emotes = parse_emote_ranges("25:0-4,12-16/1902:6-10/bad:x-1");
+        let spans: Vec<_> = emotes.iter().map(|e| (e.emote_id.as_str(), e.start, e.end)).collect();
+        assert_eq!(spans, vec![("25", 0, 4), ("1902", 6, 10), ("25", 12, 16)]);
+    }
+
+    #[test]
+    fn test_unescapes_tag_values() {
+        assert_eq!(unescape_tag_value(r"hi\sthere\:\sok\\"), r"hi there; ok\");
+        assert_eq!(unescape_tag_value("plain"), "plain");
+    }
+}
@@ -7,18 +7,15 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::Error;
+use crate::platforms::vrchat::client::{SessionState, VRChatClient, SESSION_EXPIRED};
 use maowbot_common::traits::auth_traits::{AuthenticationPrompt, AuthenticationResponse, PlatformAuthenticator};
 use maowbot_common::models::credential::CredentialType;
 use maowbot_common::models::platform::{Platform, PlatformCredential};
 
 const VRCHAT_UA: &str = "MaowBot/1.0 cat@kittyn.cat";
 
-#[derive(Debug, serde::Deserialize)]
-struct LoginResponse {
-    #[serde(default)]
-    requires_two_factor_auth: Option<Vec<String>>,
-    // ... other fields ...
-}
+/// How long a session is assumed to last past its last successful use.
+pub(crate) const SESSION_DAYS: i64 = 30;
 
 /// A minimal VRChat authenticator that uses raw `reqwest` to do:
 ///   - GET /auth/user with Basic Auth for initial login
@@ -33,6 +30,15 @@ pub struct VRChatAuthenticator {
     is_bot: bool,
     two_factor_method: Option<String>,
     session_cookie: Option<String>,
+    /// `twoFactorAuth=` cookie from a finished 2FA step. Sent on later logins
+    /// so VRChat skips asking for a code while it's still valid.
+    two_factor_cookie: Option<String>,
+}
+
+impl Default for VRChatAuthenticator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VRChatAuthenticator {
@@ -46,6 +52,7 @@ impl VRChatAuthenticator {
             is_bot: false,
             two_factor_method: None,
             session_cookie: None,
+            two_factor_cookie: None,
         }
     }
 
@@ -64,9 +71,13 @@ impl VRChatAuthenticator {
             .build()
             .map_err(|e| Error::Auth(format!("reqwest build error: {e}")))?;
 
-        let resp = client
+        let mut req = client
             .get("https://api.vrchat.cloud/api/1/auth/user")
-            .basic_auth(username, Some(password))
+            .basic_auth(username, Some(password));
+        if let Some(cookie) = &self.two_factor_cookie {
+            req = req.header("Cookie", cookie);
+        }
+        let resp = req
             .send()
             .await
             .map_err(|e| Error::Auth(format!("VRChat login request error: {e}")))?;
@@ -84,19 +95,23 @@ impl VRChatAuthenticator {
             .await
             .map_err(|e| Error::Auth(format!("Parsing VRChat login JSON => {e}")))?;
 
+        // The auth cookie comes back even when 2FA is still required; the
+        // verify call below needs it
+        let set_cookie_headers = headers.get_all("set-cookie");
+        let auth_cookie = parse_auth_cookie_from_headers(set_cookie_headers)?;
+        self.session_cookie = Some(auth_cookie);
+
         // Check if 2FA is required
         if let Some(arr) = json_val["requiresTwoFactorAuth"].as_array() {
             if !arr.is_empty() {
                 self.two_factor_method = Some(arr[0].as_str().unwrap_or("totp").to_string());
+                // A remembered 2FA cookie VRChat no longer accepts
+                self.two_factor_cookie = None;
                 // CHANGED HERE to `__2FA_PROMPT__`
                 return Err(Error::Auth("__2FA_PROMPT__".into()));
             }
         }
 
-        let set_cookie_headers = headers.get_all("set-cookie");
-        let auth_cookie = parse_auth_cookie_from_headers(set_cookie_headers)?;
-        self.session_cookie = Some(auth_cookie);
-
         Ok(())
     }
 
@@ -106,10 +121,8 @@ impl VRChatAuthenticator {
         let method = self.two_factor_method.clone()
             .unwrap_or_else(|| "totp".into());
 
-        let username = self.username.as_ref()
-            .ok_or_else(|| Error::Auth("VRChat: missing username".into()))?;
-        let password = self.password.as_ref()
-            .ok_or_else(|| Error::Auth("VRChat: missing password".into()))?;
+        let auth_cookie = self.session_cookie.clone()
+            .ok_or_else(|| Error::Auth("VRChat: log in before sending a 2FA code".into()))?;
 
         let mut default_headers = HeaderMap::new();
         default_headers.insert(USER_AGENT, HeaderValue::from_str(VRCHAT_UA)
@@ -129,7 +142,7 @@ impl VRChatAuthenticator {
         let body_json = serde_json::json!({ "code": code });
         let resp = client
             .post(twofa_url)
-            .basic_auth(username, Some(password))
+            .header("Cookie", &auth_cookie)
             .json(&body_json)
             .send()
             .await
@@ -142,8 +155,10 @@ impl VRChatAuthenticator {
         }
 
         let set_cookie_headers = resp.headers().get_all("set-cookie");
-        let auth_cookie = parse_auth_cookie_from_headers(set_cookie_headers)?;
-        self.session_cookie = Some(auth_cookie);
+        if let Ok(renewed) = parse_auth_cookie_from_headers(set_cookie_headers) {
+            self.session_cookie = Some(renewed);
+        }
+        self.two_factor_cookie = parse_cookie_from_headers(resp.headers().get_all("set-cookie"), "twoFactorAuth");
 
         Ok(())
     }
@@ -153,7 +168,7 @@ impl VRChatAuthenticator {
             .ok_or_else(|| Error::Auth("No VRChat session cookie stored.".into()))?
             .clone();
         let now = Utc::now();
-        let expires_at = Some(now + Duration::days(30));
+        let expires_at = Some(now + Duration::days(SESSION_DAYS));
         let user_name = self
             .username
             .clone()
@@ -168,7 +183,10 @@ impl VRChatAuthenticator {
             user_name,
             primary_token: cookie,
             refresh_token: None,
-            additional_data: Some(json!({ "two_factor_method": self.two_factor_method })),
+            additional_data: Some(json!({
+                "two_factor_method": self.two_factor_method,
+                "two_factor_cookie": self.two_factor_cookie,
+            })),
             expires_at,
             created_at: now,
             updated_at: now,
@@ -188,6 +206,7 @@ impl PlatformAuthenticator for VRChatAuthenticator {
         self.is_bot = false;
         self.two_factor_method = None;
         self.session_cookie = None;
+        self.two_factor_cookie = None;
         Ok(())
     }
 
@@ -210,6 +229,8 @@ impl PlatformAuthenticator for VRChatAuthenticator {
             AuthenticationResponse::MultipleKeys(keys) => {
                 self.username = keys.get("username").cloned();
                 self.password = keys.get("password").cloned();
+                // Re-logins pass the previous credential's 2FA cookie along
+                self.two_factor_cookie = keys.get("two_factor_cookie").cloned().filter(|c| !c.is_empty());
 
                 let attempt = self.attempt_login().await;
                 match attempt {
@@ -236,14 +257,31 @@ impl PlatformAuthenticator for VRChatAuthenticator {
         }
     }
 
-    async fn refresh(&mut self, _credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
-        Err(Error::Auth("VRChat does not offer a refresh flow; re-login required.".into()))
+    /// VRChat has no refresh tokens; a session that still works is kept alive
+    /// and its expiry pushed out. A dead one needs a new login.
+    async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
+        let check = VRChatClient::new(&credential.primary_token)?.check_session().await?;
+        match check.state {
+            SessionState::Valid => {
+                let mut renewed = credential.clone();
+                if let Some(cookie) = check.renewed_cookie {
+                    renewed.primary_token = cookie;
+                }
+                renewed.expires_at = Some(Utc::now() + Duration::days(SESSION_DAYS));
+                renewed.updated_at = Utc::now();
+                Ok(renewed)
+            }
+            SessionState::TwoFactorRequired => Err(Error::Auth(format!("{SESSION_EXPIRED}: 2FA code required"))),
+            SessionState::Expired => Err(Error::Auth(format!("{SESSION_EXPIRED}: log in again"))),
+        }
     }
 
     async fn validate(&self, credential: &PlatformCredential) -> Result<bool, Error> {
-        // Minimal check: if it starts with "auth=", assume valid.
-        // Could do a GET /auth/user check. For brevity, we do a naive check:
-        Ok(credential.primary_token.starts_with("auth="))
+        if !credential.primary_token.starts_with("auth=") {
+            return Ok(false);
+        }
+        let check = VRChatClient::new(&credential.primary_token)?.check_session().await?;
+        Ok(check.state == SessionState::Valid)
     }
 
     async fn revoke(&mut self, _credential: &PlatformCredential) -> Result<(), Error> {
@@ -266,14 +304,42 @@ impl PlatformAuthenticator for VRChatAuthenticator {
 pub(crate) fn parse_auth_cookie_from_headers(
     set_cookie_headers: reqwest::header::GetAll<reqwest::header::HeaderValue>
 ) -> Result<String, Error> {
-    for value in set_cookie_headers {
-        if let Ok(val_str) = value.to_str() {
-            if val_str.starts_with("auth=") {
-                let semicolon_pos = val_str.find(';').unwrap_or(val_str.len());
-                let cookie_sub = &val_str[..semicolon_pos];
-                return Ok(cookie_sub.to_string());
-            }
-        }
+    parse_cookie_from_headers(set_cookie_headers, "auth")
+        .ok_or_else(|| Error::Auth("Could not find 'auth=' cookie in Set-Cookie".into()))
+}
+
+/// The `name=value` pair of cookie `name` from the "Set-Cookie" headers, if set
+/// to something non-empty (VRChat clears cookies by setting them empty).
+pub(crate) fn parse_cookie_from_headers(
+    set_cookie_headers: reqwest::header::GetAll<reqwest::header::HeaderValue>,
+    name: &str,
+) -> Option<String> {
+    let prefix = format!("{name}=");
+    set_cookie_headers.iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter(|val_str| val_str.starts_with(&prefix))
+        .map(|val_str| &val_str[..val_str.find(';').unwrap_or(val_str.len())])
+        .find(|cookie| cookie.len() > prefix.len())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue, SET_COOKIE};
+
+    #[test]
+    fn test_finds_named_cookie_and_skips_cleared_ones() {
+        let mut headers = HeaderMap::new();
+        headers.append(SET_COOKIE, HeaderValue::from_static("twoFactorAuth=; Max-Age=0; Path=/"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("auth=authcookie_abc; Path=/; HttpOnly"));
+        headers.append(SET_COOKIE, HeaderValue::from_static("twoFactorAuth=tfa_xyz; Path=/"));
+
+        assert_eq!(parse_auth_cookie_from_headers(headers.get_all(SET_COOKIE)).unwrap(), "auth=authcookie_abc");
+        assert_eq!(
+            parse_cookie_from_headers(headers.get_all(SET_COOKIE), "twoFactorAuth").as_deref(),
+            Some("twoFactorAuth=tfa_xyz")
+        );
+        assert!(parse_cookie_from_headers(headers.get_all(SET_COOKIE), "missing").is_none());
     }
-    Err(Error::Auth("Could not find 'auth=' cookie in Set-Cookie".into()))
 }
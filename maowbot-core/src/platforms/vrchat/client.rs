@@ -1,5 +1,6 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
 use crate::Error;
 use tracing::{info, warn, error};
 use tokio::time::sleep;
@@ -29,6 +30,29 @@ pub struct VRChatWorldInfo {
 
     /// E.g. "public", "private", "hidden", "all ...", or "community labs"
     pub release_status: Option<String>,
+
+    /// Unity build targets the world ships for, e.g. "standalonewindows", "android"
+    #[serde(default)]
+    pub platforms: Vec<String>,
+}
+
+impl VRChatWorldInfo {
+    /// "PC, Quest"-style list of the platforms the world supports.
+    pub fn platform_summary(&self) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        for platform in &self.platforms {
+            let name = match platform.as_str() {
+                "standalonewindows" => "PC",
+                "android" => "Quest",
+                "ios" => "iOS",
+                other => other,
+            };
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names.join(", ")
+    }
 }
 
 /// Minimal struct for returning “current avatar.”
@@ -46,6 +70,69 @@ pub struct VRChatInstanceInfo {
     pub location: Option<String>,
 }
 
+/// An online friend and where they are, from “GET /auth/user/friends”.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct VRChatFriend {
+    pub id: String,
+    pub display_name: String,
+    /// "wrld_...:12345~..." in a visible instance; "private", "traveling"
+    /// or "offline" otherwise
+    pub location: String,
+}
+
+/// Friends are listed in pages of at most this many.
+const FRIENDS_PAGE_SIZE: usize = 100;
+
+/// A VRChat group, from “GET /groups/{groupId}”.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct VRChatGroup {
+    pub id: String,
+    pub name: String,
+    /// With `discriminator`, the "ABCDE.1234" code people search for
+    pub short_code: String,
+    pub discriminator: String,
+    pub member_count: u32,
+}
+
+/// Someone asking to join a group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VRChatGroupJoinRequest {
+    pub user_id: String,
+    pub display_name: String,
+    pub requested_at: Option<DateTime<Utc>>,
+}
+
+/// JSON shape for the members listed by “GET /groups/{groupId}/requests”.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+struct VRChatGroupMemberJson {
+    user_id: String,
+    created_at: Option<DateTime<Utc>>,
+    user: VRChatGroupMemberUserJson,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+struct VRChatGroupMemberUserJson {
+    display_name: String,
+}
+
+/// JSON shape for “POST /groups/{groupId}/posts”.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct VRChatGroupPostJson {
+    id: String,
+}
+
+/// Join requests are listed in pages of at most this many.
+const GROUP_REQUESTS_PAGE_SIZE: usize = 100;
+
 /// JSON shape for “GET /users/{userId}”.
 #[derive(Debug, Deserialize)]
 #[serde(default)]
@@ -77,8 +164,9 @@ impl Default for VRChatUserPublicApiJson {
 }
 
 /// JSON shape for `GET /auth/user` – used just to get your own userId.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 #[serde(default)]
+#[serde(rename_all = "camelCase")]
 struct VRChatAuthUserJson {
     pub id: String,
     pub display_name: String,
@@ -86,23 +174,53 @@ struct VRChatAuthUserJson {
     pub current_world: Option<String>,
     pub state: Option<String>,
     pub status: Option<String>,
+    /// Present instead of the user when the session still needs a 2FA code
+    pub requires_two_factor_auth: Option<Vec<String>>,
 }
 
-impl Default for VRChatAuthUserJson {
-    fn default() -> Self {
-        Self {
-            id: String::new(),
-            display_name: String::new(),
-            current_avatar: String::new(),
-            current_world: None,
-            state: None,
-            status: None,
-        }
+/// Start of every error for a session VRChat no longer accepts.
+pub const SESSION_EXPIRED: &str = "VRChat session expired";
+
+/// True when `e` means the VRChat login has to be redone.
+pub fn is_session_expired(e: &Error) -> bool {
+    matches!(e, Error::Auth(msg) if msg.starts_with(SESSION_EXPIRED))
+}
+
+fn session_expired(detail: &str) -> Error {
+    Error::Auth(format!("{SESSION_EXPIRED}: {detail}"))
+}
+
+/// Whether VRChat still accepts a session cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    Valid,
+    /// Logged in, but the 2FA step was never finished for this cookie
+    TwoFactorRequired,
+    Expired,
+}
+
+/// What a successful `/auth/user` reply says about the session.
+fn session_state(user: &VRChatAuthUserJson) -> SessionState {
+    if user.requires_two_factor_auth.as_ref().is_some_and(|m| !m.is_empty()) {
+        SessionState::TwoFactorRequired
+    } else if user.id.is_empty() {
+        SessionState::Expired
+    } else {
+        SessionState::Valid
     }
 }
 
+/// Result of `VRChatClient::check_session`.
+#[derive(Debug, Clone)]
+pub struct SessionCheck {
+    pub state: SessionState,
+    pub display_name: Option<String>,
+    /// A new `auth=` cookie VRChat handed back, to store in place of the old one
+    pub renewed_cookie: Option<String>,
+}
+
 /// JSON shape for “GET /worlds/...”
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 #[serde(rename_all = "camelCase")]
 struct VRChatWorldJson {
@@ -116,40 +234,24 @@ struct VRChatWorldJson {
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
     pub release_status: Option<String>,
+    pub unity_packages: Vec<VRChatUnityPackageJson>,
 }
 
-impl Default for VRChatWorldJson {
-    fn default() -> Self {
-        Self {
-            id: String::new(),
-            name: String::new(),
-            author_name: String::new(),
-            capacity: 0,
-            description: None,
-            publication_date: None,
-            updated_at: None,
-            release_status: None,
-        }
-    }
+/// One build of a world, from its `unityPackages`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct VRChatUnityPackageJson {
+    pub platform: String,
 }
 
 /// JSON shape for “GET /avatars/...”
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 #[serde(default)]
 struct VRChatAvatarJson {
     pub id: String,
     pub name: String,
 }
 
-impl Default for VRChatAvatarJson {
-    fn default() -> Self {
-        Self {
-            id: String::new(),
-            name: String::new(),
-        }
-    }
-}
-
 impl VRChatClient {
     /// Creates a new VRChatClient with the given `session_cookie`.
     pub fn new(session_cookie: &str) -> Result<Self, Error> {
@@ -164,6 +266,48 @@ impl VRChatClient {
         })
     }
 
+    /// Turns a failed response into an error; 401 means the session is gone.
+    async fn http_error(resp: reqwest::Response, what: &str) -> Error {
+        let st = resp.status();
+        let txt = resp.text().await.unwrap_or_default();
+        if st == reqwest::StatusCode::UNAUTHORIZED {
+            session_expired(&format!("{what} => HTTP {st}"))
+        } else {
+            Error::Platform(format!("{what} => HTTP {st}, {txt}"))
+        }
+    }
+
+    /// Asks `/auth/user` whether the session is still good. Calling this
+    /// regularly also keeps the session from idling out.
+    pub async fn check_session(&self) -> Result<SessionCheck, Error> {
+        let resp = self.http_client
+            .get("https://api.vrchat.cloud/api/1/auth/user")
+            .header("Cookie", &self.session_cookie)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("check_session: request failed => {e}")))?;
+
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(SessionCheck { state: SessionState::Expired, display_name: None, renewed_cookie: None });
+        }
+        if !resp.status().is_success() {
+            return Err(Self::http_error(resp, "VRChat /auth/user").await);
+        }
+
+        let renewed_cookie = crate::platforms::vrchat::auth::parse_auth_cookie_from_headers(resp.headers().get_all("set-cookie"))
+            .ok()
+            .filter(|c| *c != self.session_cookie);
+        let user_json: VRChatAuthUserJson = resp.json().await.map_err(|e| {
+            Error::Platform(format!("Parsing VRChatAuthUserJson => {e}"))
+        })?;
+
+        Ok(SessionCheck {
+            state: session_state(&user_json),
+            display_name: Some(user_json.display_name).filter(|n| !n.is_empty()),
+            renewed_cookie,
+        })
+    }
+
     /// Fetch your own userId from `/auth/user`.
     async fn fetch_current_user_id(&self) -> Result<String, Error> {
         let url = "https://api.vrchat.cloud/api/1/auth/user";
@@ -175,18 +319,16 @@ impl VRChatClient {
             .map_err(|e| Error::Platform(format!("fetch_current_user_id: request failed => {e}")))?;
 
         if !resp.status().is_success() {
-            let st = resp.status();
-            let txt = resp.text().await.unwrap_or_default();
-            return Err(Error::Platform(
-                format!("VRChat /auth/user failed: HTTP {st} => {txt}")
-            ));
+            return Err(Self::http_error(resp, "VRChat /auth/user").await);
         }
 
         let user_json: VRChatAuthUserJson = resp.json().await.map_err(|e| {
             Error::Platform(format!("Parsing VRChatAuthUserJson => {e}"))
         })?;
 
-        if user_json.id.is_empty() {
+        if user_json.requires_two_factor_auth.is_some_and(|m| !m.is_empty()) {
+            Err(session_expired("two-factor login was never completed"))
+        } else if user_json.id.is_empty() {
             Err(Error::Platform("No userId returned by VRChat /auth/user.".to_string()))
         } else {
             Ok(user_json.id)
@@ -204,11 +346,7 @@ impl VRChatClient {
             .map_err(|e| Error::Platform(format!("fetch_user_public: request => {e}")))?;
 
         if !resp.status().is_success() {
-            let st = resp.status();
-            let txt = resp.text().await.unwrap_or_default();
-            return Err(Error::Platform(
-                format!("VRChat GET /users/{user_id} => HTTP {st}, {txt}")
-            ));
+            return Err(Self::http_error(resp, &format!("VRChat GET /users/{user_id}")).await);
         }
 
         let parsed: VRChatUserPublicApiJson = resp.json().await.map_err(|e| {
@@ -226,7 +364,7 @@ impl VRChatClient {
                 Ok(id) => id,
                 Err(e) => {
                     error!("Failed to get local userId => {e}");
-                    if attempt < 3 {
+                    if attempt < 3 && !is_session_expired(&e) {
                         warn!("Will retry in 5 seconds...");
                         sleep(std::time::Duration::from_secs(5)).await;
                         continue;
@@ -239,7 +377,7 @@ impl VRChatClient {
                 Ok(info) => info,
                 Err(e) => {
                     error!("Failed to fetch /users => {e}");
-                    if attempt < 3 {
+                    if attempt < 3 && !is_session_expired(&e) {
                         warn!("Will retry in 5 seconds...");
                         sleep(std::time::Duration::from_secs(5)).await;
                         continue;
@@ -281,7 +419,7 @@ impl VRChatClient {
                 Ok(id) => id,
                 Err(e) => {
                     error!("Failed to get local userId => {e}");
-                    if attempt < 3 {
+                    if attempt < 3 && !is_session_expired(&e) {
                         warn!("Will retry in 5 seconds...");
                         sleep(std::time::Duration::from_secs(5)).await;
                         continue;
@@ -294,7 +432,7 @@ impl VRChatClient {
                 Ok(info) => info,
                 Err(e) => {
                     error!("Failed to fetch /users => {e}");
-                    if attempt < 3 {
+                    if attempt < 3 && !is_session_expired(&e) {
                         warn!("Will retry in 5 seconds...");
                         sleep(std::time::Duration::from_secs(5)).await;
                         continue;
@@ -344,11 +482,7 @@ impl VRChatClient {
             .map_err(|e| Error::Platform(format!("fetch_current_avatar_api => {e}")))?;
 
         if !resp.status().is_success() {
-            let st = resp.status();
-            let txt = resp.text().await.unwrap_or_default();
-            return Err(Error::Platform(
-                format!("VRChat /auth/user?details=all => HTTP {st}, {txt}")
-            ));
+            return Err(Self::http_error(resp, "VRChat /auth/user?details=all").await);
         }
 
         let user_json: VRChatAuthUserJson = resp.json().await.map_err(|e| {
@@ -373,11 +507,7 @@ impl VRChatClient {
             .map_err(|e| Error::Platform(format!("VRChat fetch_world_info() request failed: {e}")))?;
 
         if !resp.status().is_success() {
-            let st = resp.status();
-            let txt = resp.text().await.unwrap_or_default();
-            return Err(Error::Platform(
-                format!("VRChat GET /worlds/{world_id} => HTTP {st}, {txt}")
-            ));
+            return Err(Self::http_error(resp, &format!("VRChat GET /worlds/{world_id}")).await);
         }
 
         let wj = resp.json::<VRChatWorldJson>().await
@@ -392,6 +522,7 @@ impl VRChatClient {
             published_at: wj.publication_date,
             updated_at: wj.updated_at,
             release_status: wj.release_status,
+            platforms: wj.unity_packages.into_iter().map(|p| p.platform).filter(|p| !p.is_empty()).collect(),
         })
     }
 
@@ -406,11 +537,7 @@ impl VRChatClient {
             .map_err(|e| Error::Platform(format!("VRChat fetch_avatar_info() request failed: {e}")))?;
 
         if !resp.status().is_success() {
-            let st = resp.status();
-            let txt = resp.text().await.unwrap_or_default();
-            return Err(Error::Platform(
-                format!("VRChat GET /avatars/{avatar_id} => HTTP {st}, {txt}")
-            ));
+            return Err(Self::http_error(resp, &format!("VRChat GET /avatars/{avatar_id}")).await);
         }
 
         let aj = resp.json::<VRChatAvatarJson>().await
@@ -422,6 +549,140 @@ impl VRChatClient {
         })
     }
 
+    /// The user's current location ("wrld_...:12345~..."), once, without the
+    /// retries of `fetch_current_instance_api`. None when offline or hidden.
+    pub async fn fetch_current_location(&self) -> Result<Option<String>, Error> {
+        let my_user_id = self.fetch_current_user_id().await?;
+        let public_info = self.fetch_user_public(&my_user_id).await?;
+        Ok(public_info.location.filter(|l| l.starts_with("wrld_")))
+    }
+
+    /// Every friend who is online right now, with their location.
+    pub async fn fetch_online_friends(&self) -> Result<Vec<VRChatFriend>, Error> {
+        let mut friends = Vec::new();
+        loop {
+            let url = format!(
+                "https://api.vrchat.cloud/api/1/auth/user/friends?offline=false&n={}&offset={}",
+                FRIENDS_PAGE_SIZE, friends.len()
+            );
+            let resp = self.http_client
+                .get(&url)
+                .header("Cookie", &self.session_cookie)
+                .send()
+                .await
+                .map_err(|e| Error::Platform(format!("fetch_online_friends: request failed => {e}")))?;
+
+            if !resp.status().is_success() {
+                return Err(Self::http_error(resp, "VRChat GET /auth/user/friends").await);
+            }
+
+            let page: Vec<VRChatFriend> = resp.json().await
+                .map_err(|e| Error::Platform(format!("Parsing VRChat friends => {e}")))?;
+            let done = page.len() < FRIENDS_PAGE_SIZE;
+            friends.extend(page);
+            if done {
+                return Ok(friends);
+            }
+        }
+    }
+
+    /// Group details, to check a configured group id and show its name.
+    pub async fn fetch_group(&self, group_id: &str) -> Result<VRChatGroup, Error> {
+        let url = format!("https://api.vrchat.cloud/api/1/groups/{group_id}");
+        let resp = self.http_client
+            .get(&url)
+            .header("Cookie", &self.session_cookie)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("VRChat fetch_group() request failed: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::http_error(resp, &format!("VRChat GET /groups/{group_id}")).await);
+        }
+
+        resp.json::<VRChatGroup>().await
+            .map_err(|e| Error::Platform(format!("Parsing VRChat group => {e}")))
+    }
+
+    /// Pending join requests; needs the group-members-manage permission.
+    pub async fn fetch_group_join_requests(&self, group_id: &str) -> Result<Vec<VRChatGroupJoinRequest>, Error> {
+        let mut requests = Vec::new();
+        loop {
+            let url = format!(
+                "https://api.vrchat.cloud/api/1/groups/{group_id}/requests?n={}&offset={}",
+                GROUP_REQUESTS_PAGE_SIZE, requests.len()
+            );
+            let resp = self.http_client
+                .get(&url)
+                .header("Cookie", &self.session_cookie)
+                .send()
+                .await
+                .map_err(|e| Error::Platform(format!("fetch_group_join_requests: request failed => {e}")))?;
+
+            if !resp.status().is_success() {
+                return Err(Self::http_error(resp, &format!("VRChat GET /groups/{group_id}/requests")).await);
+            }
+
+            let page: Vec<VRChatGroupMemberJson> = resp.json().await
+                .map_err(|e| Error::Platform(format!("Parsing VRChat group requests => {e}")))?;
+            let done = page.len() < GROUP_REQUESTS_PAGE_SIZE;
+            requests.extend(page.into_iter().map(|m| VRChatGroupJoinRequest {
+                user_id: m.user_id,
+                display_name: m.user.display_name,
+                requested_at: m.created_at,
+            }));
+            if done {
+                return Ok(requests);
+            }
+        }
+    }
+
+    /// Accepts or rejects a join request.
+    pub async fn respond_group_join_request(&self, group_id: &str, user_id: &str, accept: bool) -> Result<(), Error> {
+        let url = format!("https://api.vrchat.cloud/api/1/groups/{group_id}/requests/{user_id}");
+        let body = serde_json::json!({ "action": if accept { "accept" } else { "reject" } });
+        let resp = self.http_client
+            .put(&url)
+            .header("Cookie", &self.session_cookie)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("VRChat respond_group_join_request failed: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::http_error(resp, &format!("VRChat PUT /groups/{group_id}/requests/{user_id}")).await);
+        }
+        Ok(())
+    }
+
+    /// Posts an announcement to the group; `notify` pushes it to members too.
+    /// Returns the post id.
+    pub async fn post_group_announcement(&self, group_id: &str, title: &str, text: &str, notify: bool) -> Result<String, Error> {
+        let url = format!("https://api.vrchat.cloud/api/1/groups/{group_id}/posts");
+        let body = serde_json::json!({
+            "title": title,
+            "text": text,
+            "sendNotification": notify,
+            "visibility": "group",
+        });
+        let resp = self.http_client
+            .post(&url)
+            .header("Cookie", &self.session_cookie)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| Error::Platform(format!("VRChat post_group_announcement failed: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(Self::http_error(resp, &format!("VRChat POST /groups/{group_id}/posts")).await);
+        }
+
+        let post: VRChatGroupPostJson = resp.json().await
+            .map_err(|e| Error::Platform(format!("Parsing VRChat group post => {e}")))?;
+        info!("Posted to VRChat group {group_id} ({})", post.id);
+        Ok(post.id)
+    }
+
     /// Change to a new avatar by ID. (Stub or partial)
     pub async fn select_avatar(&self, avatar_id: &str) -> Result<(), Error> {
         let url = format!("https://api.vrchat.cloud/api/1/avatars/{avatar_id}/select");
@@ -433,14 +694,27 @@ impl VRChatClient {
             .map_err(|e| Error::Platform(format!("VRChat select_avatar request failed: {e}")))?;
 
         if !resp.status().is_success() {
-            let st = resp.status();
-            let txt = resp.text().await.unwrap_or_default();
-            return Err(Error::Platform(
-                format!("Selecting VRChat avatar {avatar_id} => HTTP {st}, {txt}")
-            ));
+            return Err(Self::http_error(resp, &format!("Selecting VRChat avatar {avatar_id}")).await);
         }
 
         info!("Successfully selected avatar {avatar_id} on VRChat.");
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_of(body: &str) -> SessionState {
+        session_state(&serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_auth_user_reply_maps_to_session_state() {
+        assert_eq!(state_of(r#"{"id":"usr_1","displayName":"Maow"}"#), SessionState::Valid);
+        assert_eq!(state_of(r#"{"requiresTwoFactorAuth":["totp","otp"]}"#), SessionState::TwoFactorRequired);
+        assert_eq!(state_of(r#"{"id":"usr_1","requiresTwoFactorAuth":[]}"#), SessionState::Valid);
+        assert_eq!(state_of(r#"{}"#), SessionState::Expired);
+    }
+}
@@ -0,0 +1,147 @@
+// File: src/platforms/vrchat/location.rs
+//
+// VRChat locations look like
+// `wrld_<uuid>:12345~hidden(usr_<uuid>)~region(eu)~nonce(...)`: the world,
+// then the instance name followed by `~tag(value)` pairs that decide who may
+// join. This parses them and builds the links shared in chat.
+
+use std::fmt;
+
+/// Who may join an instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceAccess {
+    Public,
+    FriendsPlus,
+    Friends,
+    InvitePlus,
+    Invite,
+    Group,
+}
+
+impl InstanceAccess {
+    /// Anyone with the link can join (friends-only instances still need the
+    /// viewer to be the streamer's friend, which VRChat checks itself).
+    pub fn is_joinable(self) -> bool {
+        !matches!(self, InstanceAccess::Invite | InstanceAccess::InvitePlus)
+    }
+}
+
+impl fmt::Display for InstanceAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceAccess::Public => write!(f, "public"),
+            InstanceAccess::FriendsPlus => write!(f, "friends+"),
+            InstanceAccess::Friends => write!(f, "friends"),
+            InstanceAccess::InvitePlus => write!(f, "invite+"),
+            InstanceAccess::Invite => write!(f, "invite"),
+            InstanceAccess::Group => write!(f, "group"),
+        }
+    }
+}
+
+/// A parsed `world:instance` location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceLocation {
+    pub world_id: String,
+    /// The full instance id, tags included, as VRChat expects it in links
+    pub instance_id: String,
+    pub access: InstanceAccess,
+    pub region: Option<String>,
+}
+
+impl InstanceLocation {
+    /// None for "offline", "private", "traveling" and other non-locations.
+    pub fn parse(location: &str) -> Option<Self> {
+        let (world_id, instance_id) = location.split_once(':')?;
+        if !world_id.starts_with("wrld_") || instance_id.is_empty() {
+            return None;
+        }
+        let mut access = InstanceAccess::Public;
+        let mut can_request_invite = false;
+        let mut region = None;
+        for tag in instance_id.split('~').skip(1) {
+            let (name, value) = match tag.split_once('(') {
+                Some((name, rest)) => (name, rest.trim_end_matches(')')),
+                None => (tag, ""),
+            };
+            match name {
+                "hidden" => access = InstanceAccess::FriendsPlus,
+                "friends" => access = InstanceAccess::Friends,
+                "private" => access = InstanceAccess::Invite,
+                "canRequestInvite" => can_request_invite = true,
+                "group" => access = InstanceAccess::Group,
+                "region" if !value.is_empty() => region = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        if access == InstanceAccess::Invite && can_request_invite {
+            access = InstanceAccess::InvitePlus;
+        }
+        Some(Self {
+            world_id: world_id.to_string(),
+            instance_id: instance_id.to_string(),
+            access,
+            region,
+        })
+    }
+
+    /// Builds a location from the separate world and instance ids VRChat
+    /// returns for a user.
+    pub fn from_parts(world_id: &str, instance_id: &str) -> Option<Self> {
+        Self::parse(&format!("{world_id}:{instance_id}"))
+    }
+
+    /// The link to share in chat: a launch link for joinable instances, the
+    /// world page otherwise.
+    pub fn invite_link(&self) -> String {
+        if self.access.is_joinable() {
+            format!(
+                "https://vrchat.com/home/launch?worldId={}&instanceId={}",
+                self.world_id, self.instance_id
+            )
+        } else {
+            world_link(&self.world_id)
+        }
+    }
+}
+
+/// The world's page on vrchat.com.
+pub fn world_link(world_id: &str) -> String {
+    format!("https://vrchat.com/home/world/{world_id}/info")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_access_and_region() {
+        let loc = InstanceLocation::parse("wrld_abc:12345~hidden(usr_x)~region(eu)~nonce(n)").unwrap();
+        assert_eq!(loc.world_id, "wrld_abc");
+        assert_eq!(loc.instance_id, "12345~hidden(usr_x)~region(eu)~nonce(n)");
+        assert_eq!(loc.access, InstanceAccess::FriendsPlus);
+        assert_eq!(loc.region.as_deref(), Some("eu"));
+
+        let public = InstanceLocation::parse("wrld_abc:777").unwrap();
+        assert_eq!(public.access, InstanceAccess::Public);
+        assert_eq!(public.region, None);
+
+        let invite_plus = InstanceLocation::parse("wrld_abc:1~private(usr_x)~canRequestInvite").unwrap();
+        assert_eq!(invite_plus.access, InstanceAccess::InvitePlus);
+
+        assert!(InstanceLocation::parse("private").is_none());
+        assert!(InstanceLocation::parse("offline").is_none());
+        assert!(InstanceLocation::parse("traveling:traveling").is_none());
+    }
+
+    #[test]
+    fn test_invite_links_only_for_joinable_instances() {
+        let public = InstanceLocation::parse("wrld_abc:777~region(us)").unwrap();
+        assert_eq!(
+            public.invite_link(),
+            "https://vrchat.com/home/launch?worldId=wrld_abc&instanceId=777~region(us)"
+        );
+        let invite = InstanceLocation::parse("wrld_abc:1~private(usr_x)").unwrap();
+        assert_eq!(invite.invite_link(), "https://vrchat.com/home/world/wrld_abc/info");
+    }
+}
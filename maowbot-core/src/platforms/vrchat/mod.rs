@@ -2,9 +2,29 @@
 
 pub mod auth;
 pub mod client;
+pub mod location;
+pub mod world_cache;
 
 pub use client::VRChatClient;
 pub use client::VRChatWorldInfo;
 pub use client::VRChatAvatarInfo;
 
 pub use auth::VRChatAuthenticator;
+
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// The credential of the account set as `vrchat_active_account` (the one
+/// `!world` and `!instance` use), "broadcaster" when unset.
+pub async fn active_credential(
+    credentials_repo: &(dyn CredentialsRepository + Send + Sync),
+    settings: &SettingsRegistry,
+) -> Result<Option<PlatformCredential>, Error> {
+    let account = settings.get("vrchat_active_account")
+        .filter(|a| !a.trim().is_empty())
+        .unwrap_or_else(|| "broadcaster".to_string());
+    let creds = credentials_repo.list_credentials_for_platform(&Platform::VRChat).await?;
+    Ok(creds.into_iter().find(|c| c.user_name.eq_ignore_ascii_case(&account)))
+}
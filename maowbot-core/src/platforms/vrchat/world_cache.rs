@@ -0,0 +1,96 @@
+// File: src/platforms/vrchat/world_cache.rs
+//
+// Keeps `!world`, `!instance` and presence polling from asking VRChat the same
+// questions over and over. World metadata rarely changes and is kept for
+// `vrchat.world_cache_minutes`; where an account is changes often and is kept
+// for `vrchat.location_cache_seconds`, just long enough to absorb a chat
+// spamming the commands. When VRChat can't be reached a stale world is served
+// rather than an error.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tracing::debug;
+
+use crate::platforms::vrchat::client::{VRChatClient, VRChatInstanceInfo, VRChatWorldInfo};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// How long cached answers stay fresh.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheTtls {
+    pub world: Duration,
+    pub location: Duration,
+}
+
+impl CacheTtls {
+    pub fn from_settings(settings: &SettingsRegistry) -> Self {
+        Self {
+            world: Duration::from_secs(settings.get_u64("vrchat.world_cache_minutes").unwrap_or(60) * 60),
+            location: Duration::from_secs(settings.get_u64("vrchat.location_cache_seconds").unwrap_or(30)),
+        }
+    }
+}
+
+struct Cached<T> {
+    value: T,
+    fetched_at: Instant,
+}
+
+impl<T: Clone> Cached<T> {
+    fn fresh(&self, ttl: Duration) -> Option<T> {
+        (self.fetched_at.elapsed() < ttl).then(|| self.value.clone())
+    }
+}
+
+#[derive(Default)]
+pub struct WorldCache {
+    worlds: Mutex<HashMap<String, Cached<VRChatWorldInfo>>>,
+    /// account name => where it was
+    locations: Mutex<HashMap<String, Cached<Option<VRChatInstanceInfo>>>>,
+}
+
+static WORLD_CACHE: Lazy<WorldCache> = Lazy::new(WorldCache::default);
+
+/// The process-wide cache; clients are created per request, so it can't live on them.
+pub fn world_cache() -> &'static WorldCache {
+    &WORLD_CACHE
+}
+
+impl WorldCache {
+    /// World metadata, from the cache while it's younger than `ttl`.
+    pub async fn world(&self, client: &VRChatClient, world_id: &str, ttl: Duration) -> Result<VRChatWorldInfo, Error> {
+        if let Some(world) = self.worlds.lock().get(world_id).and_then(|c| c.fresh(ttl)) {
+            return Ok(world);
+        }
+        match client.fetch_world_info(world_id).await {
+            Ok(world) => {
+                self.worlds.lock().insert(world_id.to_string(), Cached { value: world.clone(), fetched_at: Instant::now() });
+                Ok(world)
+            }
+            Err(e) => {
+                let stale = self.worlds.lock().get(world_id).map(|c| c.value.clone());
+                match stale {
+                    Some(world) => {
+                        debug!("[WorldCache] serving stale {} after: {}", world_id, e);
+                        Ok(world)
+                    }
+                    None => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Where `account` is (None while offline or hidden), from the cache while
+    /// it's younger than `ttl`. Errors are never cached.
+    pub async fn location(&self, client: &VRChatClient, account: &str, ttl: Duration) -> Result<Option<VRChatInstanceInfo>, Error> {
+        let key = account.to_lowercase();
+        if let Some(location) = self.locations.lock().get(&key).and_then(|c| c.fresh(ttl)) {
+            return Ok(location);
+        }
+        let location = client.fetch_current_instance_api().await?;
+        self.locations.lock().insert(key, Cached { value: location.clone(), fetched_at: Instant::now() });
+        Ok(location)
+    }
+}
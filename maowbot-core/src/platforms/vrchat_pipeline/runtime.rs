@@ -44,6 +44,12 @@ pub struct VRChatPlatform {
     write_shutdown_handle: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
+impl Default for VRChatPlatform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl VRChatPlatform {
     pub fn new() -> Self {
         Self {
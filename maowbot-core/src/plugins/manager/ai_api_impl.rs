@@ -126,8 +126,8 @@ impl AiApi for AiApiImpl {
                 
                 // If we're just updating the model for web search, still pass it through
                 // to ensure the provider gets updated properly with the options
-                if config.get("default_model").map_or(false, |m| m == "gpt-4o-search-preview") &&
-                   config.get("options").map_or(false, |o| o.get("enable_web_search").map_or(false, |v| v == "true")) &&
+                if config.get("default_model").is_some_and(|m| m == "gpt-4o-search-preview") &&
+                   config.get("options").is_some_and(|o| o.get("enable_web_search").is_some_and(|v| v == "true")) &&
                    config.get("api_key").is_none() {
                     
                     tracing::info!("Updating model to gpt-4o-search-preview for web search");
@@ -25,6 +25,10 @@ impl BotConfigApi for PluginManager {
     }
 
     async fn set_bot_config_value(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        // Validated and applied right away when the settings registry is attached
+        if let Some(settings) = &self.settings {
+            return settings.set(config_key, config_value).await;
+        }
         let auth_mgr_arc = self.auth_manager
             .as_ref()
             .ok_or_else(|| Error::Auth("No auth manager".into()))?;
@@ -33,6 +37,9 @@ impl BotConfigApi for PluginManager {
     }
 
     async fn delete_bot_config_key(&self, config_key: &str) -> Result<(), Error> {
+        if let Some(settings) = &self.settings {
+            return settings.delete(config_key).await;
+        }
         let auth_mgr_arc = self.auth_manager
             .as_ref()
             .ok_or_else(|| Error::Auth("No auth manager".into()))?;
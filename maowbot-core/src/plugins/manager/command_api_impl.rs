@@ -35,16 +35,12 @@ impl CommandApi for PluginManager {
 
     // usage logs
     async fn get_usage_for_command(&self, command_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
-        let usage_repo = match &self.command_usage_repo {
-            repo => repo.clone(),
-        };
+        let usage_repo = self.command_usage_repo.clone();
         usage_repo.list_usage_for_command(command_id, limit).await
     }
 
     async fn get_usage_for_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
-        let usage_repo = match &self.command_usage_repo {
-            repo => repo.clone(),
-        };
+        let usage_repo = self.command_usage_repo.clone();
         usage_repo.list_usage_for_user(user_id, limit).await
     }
 
@@ -58,8 +54,6 @@ impl CommandApi for PluginManager {
 // Helper method on PluginManager to get CommandService
 impl PluginManager {
     pub fn resolve_command_service(&self) -> Result<Arc<crate::services::CommandService>, Error> {
-        match &self.command_service {
-            svc => Ok(svc.clone()),
-        }
+        Ok(self.command_service.clone())
     }
 }
\ No newline at end of file
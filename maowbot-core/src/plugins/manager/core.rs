@@ -24,23 +24,19 @@ use crate::plugins::types::{
     PluginRecord,
     PluginStatesFile
 };
-use crate::repositories::postgres::user::{UserRepository};
 use crate::eventbus::db_logger_handle::DbLoggerControl;
-use maowbot_common::traits::repository_traits::{CommandUsageRepository, CredentialsRepository, RedeemUsageRepository};
+use maowbot_common::traits::repository_traits::{
+    AnalyticsRepo, ChatArchiveRepository, CommandUsageRepository, CredentialsRepository, DiscordRepository,
+    DripRepository, PlatformIdentityRepo, RedeemUsageRepository, UserAnalysisRepository, UserRepo,
+};
 use crate::platforms::manager::PlatformManager;
 use crate::plugins::manager::plugin_api_impl::build_status_response;
 // or you can keep the function local
-use crate::repositories::postgres::drip::DripRepository;
 use crate::services::{CommandService, RedeemService};
 use crate::services::user_service::UserService;
 
 use maowbot_osc::MaowOscManager;
 use crate::auth::manager::AuthManager;
-use crate::repositories::postgres::analytics::PostgresAnalyticsRepository;
-use crate::repositories::postgres::discord::PostgresDiscordRepository;
-use crate::repositories::postgres::platform_identity::PlatformIdentityRepository;
-use crate::repositories::postgres::user_analysis::PostgresUserAnalysisRepository;
-use crate::plugins::manager::ai_api_impl::AiApiImpl;
 use maowbot_common::traits::api::AiApi;
 
 /// The main manager that loads/stores plugins, spawns connections,
@@ -71,15 +67,17 @@ pub struct PluginManager {
     pub auth_manager: Option<Arc<tokio::sync::Mutex<AuthManager>>>,
 
     /// A user repository so we can create/remove user rows, etc.
-    pub user_repo: Arc<UserRepository>,
-    pub drip_repo: Arc<DripRepository>,
-    pub discord_repo: Arc<PostgresDiscordRepository>,
+    pub user_repo: Arc<dyn UserRepo + Send + Sync>,
+    pub drip_repo: Arc<dyn DripRepository>,
+    pub discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
     /// The manager for platform logic (starting/stopping runtimes, etc.).
     pub platform_manager: Arc<PlatformManager>,
 
-    pub analytics_repo: Arc<PostgresAnalyticsRepository>,
-    pub user_analysis_repo: Arc<PostgresUserAnalysisRepository>,
-    pub platform_identity_repo: Arc<PlatformIdentityRepository>,
+    pub analytics_repo: Arc<dyn AnalyticsRepo>,
+    /// Archived chat lines, for `!lastseen`.
+    pub chat_archive: Arc<dyn ChatArchiveRepository>,
+    pub user_analysis_repo: Arc<dyn UserAnalysisRepository>,
+    pub platform_identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
     pub user_service: Arc<UserService>,
 
     pub command_service: Arc<CommandService>,
@@ -108,18 +106,43 @@ pub struct PluginManager {
     // NEW: Autostart repository
     // ---------------------------------------
     pub autostart_repo: Arc<dyn crate::repositories::postgres::autostart::AutostartRepository + Send + Sync>,
+
+    /// Typed, hot-reloaded view of bot_config, if set.
+    pub settings: Option<Arc<crate::settings::SettingsRegistry>>,
+
+    /// Automatic stream markers and VOD chapter export, if set.
+    pub stream_marker_service: Option<Arc<crate::services::twitch::stream_marker_service::StreamMarkerService>>,
+
+    /// Emote usage counts for `!emotestats`, if set.
+    pub emote_stats_service: Option<Arc<crate::services::emote_stats::EmoteStatsService>>,
+
+    /// Clip polling and `!clipit`, if set.
+    pub clip_service: Option<Arc<crate::services::twitch::clip_service::ClipService>>,
+
+    /// Localized response templates, if set.
+    pub localizer: Option<Arc<crate::i18n::Localizer>>,
+
+    /// Friends in the streamer's VRChat instance for `!whosHere`, if set.
+    pub vrchat_presence_service: Option<Arc<crate::services::vrchat_presence_service::VRChatPresenceService>>,
+
+    /// Viewers in the broadcaster's Twitch chat for `!lurkers` and `!watchtime`, if set.
+    pub chatter_presence_service: Option<Arc<crate::services::twitch::chatter_presence::ChatterPresenceService>>,
+
+    /// The broadcaster's Twitch stream schedule for `!schedule`, if set.
+    pub schedule_service: Option<Arc<crate::services::twitch::schedule_service::ScheduleService>>,
 }
 
 impl PluginManager {
     /// Constructs a new `PluginManager` with empty plugin lists and so on.
     pub fn new(
         passphrase: Option<String>,
-        user_repo: Arc<UserRepository>,
-        drip_repo: Arc<DripRepository>,
-        discord_repo: Arc<PostgresDiscordRepository>,
-        analytics_repo: Arc<PostgresAnalyticsRepository>,
-        user_analysis_repo: Arc<PostgresUserAnalysisRepository>,
-        platform_identity_repo: Arc<PlatformIdentityRepository>,
+        user_repo: Arc<dyn UserRepo + Send + Sync>,
+        drip_repo: Arc<dyn DripRepository>,
+        discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
+        analytics_repo: Arc<dyn AnalyticsRepo>,
+        chat_archive: Arc<dyn ChatArchiveRepository>,
+        user_analysis_repo: Arc<dyn UserAnalysisRepository>,
+        platform_identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
         platform_manager: Arc<PlatformManager>,
         user_service: Arc<UserService>,
         command_service: Arc<CommandService>,
@@ -145,6 +168,7 @@ impl PluginManager {
             platform_manager,
 
             analytics_repo,
+            chat_archive,
             user_analysis_repo,
             platform_identity_repo,
             user_service,
@@ -160,6 +184,14 @@ impl PluginManager {
             osc_toggle_repo: None, // OSC toggle repository
             osc_toggle_service: None, // OSC toggle service
             autostart_repo,
+            settings: None,
+            stream_marker_service: None,
+            emote_stats_service: None,
+            clip_service: None,
+            localizer: None,
+            vrchat_presence_service: None,
+            chatter_presence_service: None,
+            schedule_service: None,
         };
         manager.load_plugin_states();
         manager
@@ -196,6 +228,38 @@ impl PluginManager {
     pub fn set_osc_toggle_service(&mut self, service: Arc<crate::services::osc_toggle_service::OscToggleService>) {
         self.osc_toggle_service = Some(service);
     }
+
+    pub fn set_settings_registry(&mut self, settings: Arc<crate::settings::SettingsRegistry>) {
+        self.settings = Some(settings);
+    }
+
+    pub fn set_stream_marker_service(&mut self, service: Arc<crate::services::twitch::stream_marker_service::StreamMarkerService>) {
+        self.stream_marker_service = Some(service);
+    }
+
+    pub fn set_emote_stats_service(&mut self, service: Arc<crate::services::emote_stats::EmoteStatsService>) {
+        self.emote_stats_service = Some(service);
+    }
+
+    pub fn set_clip_service(&mut self, service: Arc<crate::services::twitch::clip_service::ClipService>) {
+        self.clip_service = Some(service);
+    }
+
+    pub fn set_localizer(&mut self, localizer: Arc<crate::i18n::Localizer>) {
+        self.localizer = Some(localizer);
+    }
+
+    pub fn set_vrchat_presence_service(&mut self, service: Arc<crate::services::vrchat_presence_service::VRChatPresenceService>) {
+        self.vrchat_presence_service = Some(service);
+    }
+
+    pub fn set_chatter_presence_service(&mut self, service: Arc<crate::services::twitch::chatter_presence::ChatterPresenceService>) {
+        self.chatter_presence_service = Some(service);
+    }
+
+    pub fn set_schedule_service(&mut self, service: Arc<crate::services::twitch::schedule_service::ScheduleService>) {
+        self.schedule_service = Some(service);
+    }
     /// Subscribes the manager to events from the bus, so we can broadcast them to plugins if needed.
     pub async fn subscribe_to_event_bus(&self, bus: Arc<EventBus>) {
         let mut rx = bus.subscribe(None).await;
@@ -209,10 +273,10 @@ impl PluginManager {
                     maybe_event = rx.recv() => {
                         match maybe_event {
                             Some(event) => match event {
-                                BotEvent::ChatMessage { platform, channel, user, text, .. } => {
+                                BotEvent::ChatMessage { platform, channel, user, text, metadata, .. } => {
                                     trace!("🔴 PLUGIN MANAGER: Received event from EventBus - platform: {}, channel: {}, user: {}, text: '{}'",
                                          platform, channel, user, text);
-                                    pm_clone.handle_chat_event(&platform, &channel, &user, &text).await;
+                                    pm_clone.handle_chat_event(&platform, &channel, &user, &text, &metadata).await;
                                 },
                                 BotEvent::Tick => {
                                     // We can broadcast Tick to plugins if we want:
@@ -229,6 +293,46 @@ impl PluginManager {
                                 BotEvent::SystemMessage(msg) => {
                                     info!("(EventBus) SystemMessage => {}", msg);
                                 }
+                                BotEvent::VRChatPresence { joined, user_id, display_name, watched, .. } => {
+                                    // For the overlay's "friend joined" toasts
+                                    use maowbot_proto::plugs::{
+                                        PluginStreamResponse,
+                                        plugin_stream_response::Payload as RespPayload,
+                                        GameEvent
+                                    };
+                                    let msg = PluginStreamResponse {
+                                        payload: Some(RespPayload::GameEvent(GameEvent {
+                                            name: "vrchat_presence".to_string(),
+                                            json: serde_json::json!({
+                                                "joined": joined,
+                                                "user_id": user_id,
+                                                "display_name": display_name,
+                                                "watched": watched,
+                                            }).to_string(),
+                                        })),
+                                    };
+                                    pm_clone.broadcast(msg, None).await;
+                                }
+                                BotEvent::OpsAlert { kind, severity, title, message, desktop: true, .. } => {
+                                    // Alerts routed to the desktop show up as GUI notifications
+                                    use maowbot_proto::plugs::{
+                                        PluginStreamResponse,
+                                        plugin_stream_response::Payload as RespPayload,
+                                        GameEvent
+                                    };
+                                    let msg = PluginStreamResponse {
+                                        payload: Some(RespPayload::GameEvent(GameEvent {
+                                            name: "ops_alert".to_string(),
+                                            json: serde_json::json!({
+                                                "kind": kind.to_string(),
+                                                "severity": severity.to_string(),
+                                                "title": title,
+                                                "message": message,
+                                            }).to_string(),
+                                        })),
+                                    };
+                                    pm_clone.broadcast(msg, None).await;
+                                }
                                 _ => {}
                             },
                             None => {
@@ -250,24 +354,26 @@ impl PluginManager {
 
     /// Called internally whenever a ChatMessage event arrives. We can broadcast to plugins if they have a chat capability.
     /// Additionally, we now check if the message should be processed by the AI service
-    async fn handle_chat_event(&self, platform: &str, channel: &str, user: &str, text: &str) {
+    async fn handle_chat_event(
+        &self,
+        platform: &str,
+        channel: &str,
+        user: &str,
+        text: &str,
+        metadata: &serde_json::Map<String, serde_json::Value>,
+    ) {
         trace!("🔴 PLUGIN MANAGER: Received chat event - platform: {}, channel: {}, user: {}, text: '{}'", platform, channel, user, text);
         
         use maowbot_proto::plugs::{
             PluginStreamResponse,
             plugin_stream_response::Payload as RespPayload,
-            ChatMessage, PluginCapability
+            PluginCapability
         };
         use maowbot_common::models::platform::Platform as PlatformEnum;
 
         // First, broadcast the message to all plugins
         let msg = PluginStreamResponse {
-            payload: Some(RespPayload::ChatMessage(ChatMessage {
-                platform: platform.to_string(),
-                channel: channel.to_string(),
-                user: user.to_string(),
-                text: text.to_string(),
-            })),
+            payload: Some(RespPayload::ChatMessage(Box::new(chat_message_proto(platform, channel, user, text, metadata)))),
         };
         self.broadcast(msg, Some(PluginCapability::ReceiveChatEvents)).await;
         
@@ -287,7 +393,7 @@ impl PluginManager {
             };
             
             // Get trigger prefixes to log them for debugging
-            let prefixes = match ai_service.get_trigger_prefixes().await {
+            let _prefixes = match ai_service.get_trigger_prefixes().await {
                 Ok(p) => {
                     trace!("🔴 PLUGIN MANAGER: Successfully retrieved trigger prefixes: {:?}", p);
                     p
@@ -301,7 +407,7 @@ impl PluginManager {
             trace!("🔴 PLUGIN MANAGER: About to call should_process_with_ai for message: '{}'", text);
             
             // Check if this message would trigger AI processing
-            let should_process = match ai_service.should_process_with_ai(text).await {
+            let _should_process = match ai_service.should_process_with_ai(text).await {
                 true => {
                     trace!("🔴 PLUGIN MANAGER: Message '{}' MATCHES AI trigger - proceeding with AI processing", text);
                     true
@@ -313,12 +419,13 @@ impl PluginManager {
             };
             
             // Convert platform string to Platform enum
-            let platform_enum = match platform {
+            let _platform_enum = match platform {
                 "twitch-irc" => PlatformEnum::TwitchIRC,
                 "twitch" => PlatformEnum::Twitch,
                 "discord" => PlatformEnum::Discord,
                 "vrchat" => PlatformEnum::VRChat,
                 "twitch-eventsub" => PlatformEnum::TwitchEventSub,
+                "kick" => PlatformEnum::Kick,
                 _ => {
                     error!("Unknown platform: {}", platform);
                     return;
@@ -388,6 +495,24 @@ impl PluginManager {
                                     },
                                     Err(e) => error!("🔴 PLUGIN MANAGER: Failed to get Twitch credentials: {:?}", e),
                                 }
+                            } else if platform == "kick" {
+                                match self.credentials_repo.list_credentials_for_platform(&PlatformEnum::Kick).await {
+                                    Ok(creds) => {
+                                        if let Some(bot_cred) = creds.iter().find(|c| c.is_bot).or_else(|| creds.first()) {
+                                            match self.platform_manager.send_kick_message(
+                                                &bot_cred.user_name,
+                                                channel,
+                                                &ai_response
+                                            ).await {
+                                                Ok(_) => trace!("🔴 PLUGIN MANAGER: Successfully sent AI response to Kick channel: {}", channel),
+                                                Err(e) => error!("🔴 PLUGIN MANAGER: Failed to send AI response via Kick: {:?}", e),
+                                            }
+                                        } else {
+                                            error!("🔴 PLUGIN MANAGER: No Kick credential found for sending AI response");
+                                        }
+                                    },
+                                    Err(e) => error!("🔴 PLUGIN MANAGER: Failed to get Kick credentials: {:?}", e),
+                                }
                             } else {
                                 trace!("🔴 PLUGIN MANAGER: Platform '{}' not supported for AI responses", platform);
                             }
@@ -748,7 +873,7 @@ impl PluginManager {
         }
         unsafe {
             let lib = Library::new(&path_str)?;
-            let constructor: Symbol<unsafe extern "C" fn() -> *mut (dyn PluginConnection)> =
+            let constructor: Symbol<unsafe extern "C" fn() -> *mut dyn PluginConnection> =
                 lib.get(b"create_plugin")?;
             let raw = constructor();
             let plugin = Arc::from_raw(raw);
@@ -756,6 +881,13 @@ impl PluginManager {
             std::mem::forget(lib);
 
             inproc_conn.set_name(record.name.clone()).await;
+            for factory in inproc_conn.platform_factories() {
+                let platform = factory.name().to_string();
+                match self.platform_manager.platforms.register_owned(factory, &record.name) {
+                    Ok(()) => info!("Plugin '{}' registered platform '{}'", record.name, platform),
+                    Err(e) => warn!("Plugin '{}' could not register a platform: {}", record.name, e),
+                }
+            }
             self.add_plugin_connection(inproc_conn).await;
         }
         Ok(())
@@ -829,3 +961,76 @@ impl PluginManager {
 
 // We're not implementing the AiApi trait for PluginManager directly
 // to avoid lifetime issues. Instead, we'll use an AiApiImpl member.
+
+/// The plugin-stream ChatMessage for a chat event, with the badges, color,
+/// reply and emotes the overlay needs to draw it (Twitch IRC fills these in
+/// the event metadata).
+fn chat_message_proto(
+    platform: &str,
+    channel: &str,
+    user: &str,
+    text: &str,
+    metadata: &serde_json::Map<String, serde_json::Value>,
+) -> maowbot_proto::plugs::ChatMessage {
+    use maowbot_proto::plugs::{ChatBadge, ChatEmoteRange, ChatMessage, ChatReplyParent};
+    use crate::platforms::twitch_irc::tags::{parse_badges, parse_emote_ranges};
+
+    let meta = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let reply_id = meta("reply_parent_msg_id");
+    ChatMessage {
+        platform: platform.to_string(),
+        channel: channel.to_string(),
+        user: user.to_string(),
+        text: text.to_string(),
+        display_name: meta("username"),
+        badges: parse_badges(&meta("badges"))
+            .into_iter()
+            .map(|b| ChatBadge { set_id: b.set_id, version: b.version })
+            .collect(),
+        color: meta("color"),
+        reply_parent: (!reply_id.is_empty()).then(|| ChatReplyParent {
+            message_id: reply_id,
+            user_id: meta("reply_parent_user_id"),
+            user_login: meta("reply_parent_user_login"),
+            display_name: meta("reply_parent_display_name"),
+            text: meta("reply_parent_msg_body"),
+        }),
+        emotes: parse_emote_ranges(&meta("emotes"))
+            .into_iter()
+            .map(|e| ChatEmoteRange { emote_id: e.emote_id, start: e.start as u32, end: e.end as u32 })
+            .collect(),
+        message_id: meta("message_id"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_message_carries_twitch_tags() {
+        let mut metadata = serde_json::Map::new();
+        for (key, value) in [
+            ("username", "Mochi"),
+            ("badges", "moderator/1,subscriber/6"),
+            ("color", "#FF4500"),
+            ("emotes", "25:6-10"),
+            ("reply_parent_msg_id", "abc"),
+            ("reply_parent_display_name", "Tofu"),
+            ("reply_parent_msg_body", "hi there"),
+        ] {
+            metadata.insert(key.into(), value.into());
+        }
+        let msg = chat_message_proto("twitch-irc", "#kittyn", "uuid", "hello Kappa", &metadata);
+        assert_eq!(msg.display_name, "Mochi");
+        assert_eq!(msg.badges.len(), 2);
+        assert_eq!(msg.badges[0].set_id, "moderator");
+        assert_eq!(msg.color, "#FF4500");
+        assert_eq!((msg.emotes[0].start, msg.emotes[0].end), (6, 10));
+        let reply = msg.reply_parent.unwrap();
+        assert_eq!((reply.display_name.as_str(), reply.text.as_str()), ("Tofu", "hi there"));
+
+        let plain = chat_message_proto("discord", "general", "uuid", "hi", &serde_json::Map::new());
+        assert!(plain.badges.is_empty() && plain.reply_parent.is_none() && plain.color.is_empty());
+    }
+}
@@ -1,13 +1,10 @@
-use std::sync::Arc;
 use async_trait::async_trait;
 use maowbot_common::error::Error;
 use maowbot_common::traits::api::DiscordApi;
 use maowbot_common::models::discord::{DiscordGuildRecord, DiscordChannelRecord, DiscordEventConfigRecord, DiscordEmbed, DiscordLiveRoleRecord};
-use twilight_cache_inmemory::InMemoryCache;
-use twilight_model::id::marker::{GuildMarker};
+use twilight_model::id::marker::GuildMarker;
 use twilight_model::id::Id;
 use uuid::Uuid;
-use maowbot_common::traits::repository_traits::DiscordRepository;
 use crate::plugins::manager::PluginManager;
 
 #[async_trait]
@@ -157,7 +154,7 @@ impl DiscordApi for PluginManager {
             .map_err(|_| Error::Platform(format!("Guild ID '{guild_id_str}' not numeric")))?;
         let guild_id = Id::<GuildMarker>::new(guild_id_num);
 
-        let guild = cache.guild(guild_id)
+        let _guild = cache.guild(guild_id)
             .ok_or_else(|| Error::Platform(format!("Guild ID '{}' not found in cache", guild_id_str)))?;
 
         let mut out = Vec::new();
@@ -4,6 +4,19 @@ use maowbot_common::models::osc::{OscStatus};
 use crate::plugins::manager::core::PluginManager;
 use async_trait::async_trait;
 
+impl PluginManager {
+    /// An OSC destination from the settings registry, or straight from bot_config
+    /// when no registry is attached.
+    async fn osc_setting(&self, key: &str) -> Option<String> {
+        if let Some(settings) = &self.settings {
+            return settings.get(key);
+        }
+        let auth_mgr = self.auth_manager.as_ref()?;
+        let auth_guard = auth_mgr.lock().await;
+        auth_guard.bot_config_repo.get_value(key).await.ok().flatten()
+    }
+}
+
 #[async_trait]
 impl OscApi for PluginManager {
     async fn osc_start(&self) -> Result<(), Error> {
@@ -11,19 +24,12 @@ impl OscApi for PluginManager {
             .as_ref()
             .ok_or_else(|| Error::Platform("No OSC manager attached".to_string()))?;
         
-        // Load configured destinations from bot_config
-        if let Some(auth_mgr) = &self.auth_manager {
-            let auth_guard = auth_mgr.lock().await;
-            
-            // Load VRChat destination
-            if let Ok(Some(vrchat_dest)) = auth_guard.bot_config_repo.get_value("osc_vrchat_dest").await {
-                mgr.set_vrchat_dest(Some(vrchat_dest)).await;
-            }
-            
-            // Load Robot destination
-            if let Ok(Some(robot_dest)) = auth_guard.bot_config_repo.get_value("osc_robot_dest").await {
-                mgr.set_robot_dest(Some(robot_dest)).await;
-            }
+        // Load configured destinations
+        if let Some(vrchat_dest) = self.osc_setting("osc_vrchat_dest").await {
+            mgr.set_vrchat_dest(Some(vrchat_dest)).await;
+        }
+        if let Some(robot_dest) = self.osc_setting("osc_robot_dest").await {
+            mgr.set_robot_dest(Some(robot_dest)).await;
         }
         
         mgr.start_all()
@@ -114,11 +120,8 @@ impl OscApi for PluginManager {
             .ok_or_else(|| Error::Platform("No OSC manager attached".to_string()))?;
         
         // Load the latest VRChat destination from config
-        if let Some(auth_mgr) = &self.auth_manager {
-            let auth_guard = auth_mgr.lock().await;
-            if let Ok(Some(vrchat_dest)) = auth_guard.bot_config_repo.get_value("osc_vrchat_dest").await {
-                mgr.set_vrchat_dest(Some(vrchat_dest)).await;
-            }
+        if let Some(vrchat_dest) = self.osc_setting("osc_vrchat_dest").await {
+            mgr.set_vrchat_dest(Some(vrchat_dest)).await;
         }
         
         mgr.send_avatar_parameter_bool(name, value)
@@ -132,11 +135,8 @@ impl OscApi for PluginManager {
             .ok_or_else(|| Error::Platform("No OSC manager attached".to_string()))?;
         
         // Load the latest VRChat destination from config
-        if let Some(auth_mgr) = &self.auth_manager {
-            let auth_guard = auth_mgr.lock().await;
-            if let Ok(Some(vrchat_dest)) = auth_guard.bot_config_repo.get_value("osc_vrchat_dest").await {
-                mgr.set_vrchat_dest(Some(vrchat_dest)).await;
-            }
+        if let Some(vrchat_dest) = self.osc_setting("osc_vrchat_dest").await {
+            mgr.set_vrchat_dest(Some(vrchat_dest)).await;
         }
         
         mgr.send_avatar_parameter_int(name, value)
@@ -150,11 +150,8 @@ impl OscApi for PluginManager {
             .ok_or_else(|| Error::Platform("No OSC manager attached".to_string()))?;
         
         // Load the latest VRChat destination from config
-        if let Some(auth_mgr) = &self.auth_manager {
-            let auth_guard = auth_mgr.lock().await;
-            if let Ok(Some(vrchat_dest)) = auth_guard.bot_config_repo.get_value("osc_vrchat_dest").await {
-                mgr.set_vrchat_dest(Some(vrchat_dest)).await;
-            }
+        if let Some(vrchat_dest) = self.osc_setting("osc_vrchat_dest").await {
+            mgr.set_vrchat_dest(Some(vrchat_dest)).await;
         }
         
         mgr.send_avatar_parameter_float(name, value)
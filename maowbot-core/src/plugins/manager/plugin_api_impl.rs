@@ -1,12 +1,11 @@
 use tokio::sync::mpsc;
 
 use crate::Error;
-use crate::eventbus::{BotEvent};
+use crate::eventbus::BotEvent;
 use maowbot_common::models::analytics as common_analytics;
 use maowbot_common::traits::api::{PluginApi, CredentialsApi};
 use maowbot_common::models::plugin::{StatusData, AccountStatus};
 use crate::plugins::manager::core::PluginManager;
-use crate::repositories::postgres::user::UserRepo;
 
 /// Helper function to build a `StatusData`.
 pub async fn build_status_response(manager: &PluginManager) -> maowbot_proto::plugs::PluginStreamResponse {
@@ -18,13 +17,12 @@ pub async fn build_status_response(manager: &PluginManager) -> maowbot_proto::pl
         infos.into_iter().map(|i| i.name).collect::<Vec<_>>()
     };
     let uptime = manager.start_time.elapsed().as_secs();
-    let response = maowbot_proto::plugs::PluginStreamResponse {
+    maowbot_proto::plugs::PluginStreamResponse {
         payload: Some(RespPayload::StatusResponse(StatusResponse {
             connected_plugins: connected,
             server_uptime: uptime,
         })),
-    };
-    response
+    }
 }
 
 /// Convert our local `eventbus::BotEvent` to the new `maowbot_common::models::analytics::BotEvent`.
@@ -66,6 +64,250 @@ fn convert_event(evt: crate::eventbus::BotEvent) -> common_analytics::BotEvent {
                 data: Some(serde_json::json!({ "message": msg })),
             }
         }
+        BotEvent::CredentialRefreshFailed { credential_id, platform, user_name, expires_at, error } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "credential_refresh_failed".to_string(),
+                event_timestamp: chrono::Utc::now(),
+                data: Some(serde_json::json!({
+                    "credential_id": credential_id,
+                    "platform": platform,
+                    "user_name": user_name,
+                    "expires_at": expires_at,
+                    "error": error,
+                })),
+            }
+        }
+        BotEvent::PlatformConnectionChanged { platform, account_name, state, attempt, error, next_retry_at, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "platform_connection_changed".to_string(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "platform": platform,
+                    "account_name": account_name,
+                    "state": state,
+                    "attempt": attempt,
+                    "error": error,
+                    "next_retry_at": next_retry_at,
+                })),
+            }
+        }
+        BotEvent::ConfigChanged { key, old_value, new_value, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "config_changed".to_string(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "key": key,
+                    "old_value": old_value,
+                    "new_value": new_value,
+                })),
+            }
+        }
+        BotEvent::Donation(donation) => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "donation".to_string(),
+                event_timestamp: donation.received_at,
+                data: serde_json::to_value(&donation).ok(),
+            }
+        }
+        BotEvent::ViewerMilestone(milestone) => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "twitch.milestone".to_string(),
+                event_timestamp: milestone.celebrated_at,
+                data: serde_json::to_value(&milestone).ok(),
+            }
+        }
+        BotEvent::ObsSceneChanged { instance, scene, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "obs.scene_changed".to_string(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "instance": instance,
+                    "scene": scene,
+                })),
+            }
+        }
+        BotEvent::HeartRate { source, bpm, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "heart_rate".to_string(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "source": source,
+                    "bpm": bpm,
+                })),
+            }
+        }
+        BotEvent::VRChatPresence { joined, ref user_id, ref display_name, ref location, watched, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "joined": joined,
+                    "user_id": user_id,
+                    "display_name": display_name,
+                    "location": location,
+                    "watched": watched,
+                })),
+            }
+        }
+        BotEvent::VRChatGroup { kind, ref group_id, ref user_id, ref display_name, ref title, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: kind.event_type().to_string(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "group_id": group_id,
+                    "user_id": user_id,
+                    "display_name": display_name,
+                    "title": title,
+                })),
+            }
+        }
+        BotEvent::TwitchChatters { ref channel, ref joined, ref left, present, timestamp } => {
+            let logins = |chatters: &[crate::platforms::twitch::requests::chatters::Chatter]| {
+                chatters.iter().map(|c| c.user_login.clone()).collect::<Vec<_>>()
+            };
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "channel": channel,
+                    "joined": logins(joined),
+                    "left": logins(left),
+                    "present": present,
+                })),
+            }
+        }
+        BotEvent::TwitchScheduleStartingSoon { ref channel, ref segment_id, ref title, ref category, start_time, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "channel": channel,
+                    "segment_id": segment_id,
+                    "title": title,
+                    "category": category,
+                    "start_time": start_time.to_rfc3339(),
+                })),
+            }
+        }
+        BotEvent::HypeMoment { ref channel, kind, score, messages, emote_messages, bits, subs, started_at, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "channel": channel,
+                    "kind": kind.as_str(),
+                    "score": score,
+                    "messages": messages,
+                    "emote_messages": emote_messages,
+                    "bits": bits,
+                    "subs": subs,
+                    "started_at": started_at.to_rfc3339(),
+                })),
+            }
+        }
+        BotEvent::ResponderTriggered { ref platform, ref channel, ref user, ref platform_user_id, ref responder, ref matched, ref text, ref response, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "platform": platform,
+                    "channel": channel,
+                    "user": user,
+                    "platform_user_id": platform_user_id,
+                    "responder": responder,
+                    "matched": matched,
+                    "text": text,
+                    "response": response,
+                })),
+            }
+        }
+        BotEvent::RedeemApproval { approval_id, status, ref reward_name, ref channel, ref user, ref user_input, ref decided_by, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "approval_id": approval_id.to_string(),
+                    "status": status.to_string(),
+                    "reward_name": reward_name,
+                    "channel": channel,
+                    "user": user,
+                    "user_input": user_input,
+                    "decided_by": decided_by,
+                })),
+            }
+        }
+        BotEvent::ModerationEscalated { user_id, ref user, ref channel, ref category, ref rule, offense, ref action, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "user_id": user_id.to_string(),
+                    "user": user,
+                    "channel": channel,
+                    "category": category,
+                    "rule": rule,
+                    "offense": offense,
+                    "action": action,
+                })),
+            }
+        }
+        BotEvent::OpsAlert { kind, severity, ref key, ref title, ref message, desktop, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "kind": kind.to_string(),
+                    "severity": severity.to_string(),
+                    "key": key,
+                    "title": title,
+                    "message": message,
+                    "desktop": desktop,
+                })),
+            }
+        }
+        BotEvent::CrowdVoteDecided { ref channel, ref title, ref winner, ref control, weight, voters, timestamp } => {
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type: evt.event_type(),
+                event_timestamp: timestamp,
+                data: Some(serde_json::json!({
+                    "channel": channel,
+                    "title": title,
+                    "winner": winner,
+                    "control": control,
+                    "weight": weight,
+                    "voters": voters,
+                })),
+            }
+        }
+        BotEvent::Kick(ref data) => {
+            let event_type = evt.event_type();
+            common_analytics::BotEvent {
+                event_id: uuid::Uuid::new_v4(),
+                event_type,
+                event_timestamp: chrono::Utc::now(),
+                data: Some(serde_json::json!({
+                    "platform": "kick",
+                    "details": format!("{:?}", data)
+                })),
+            }
+        }
         BotEvent::TwitchEventSub(sub) => {
             // If desired, store more structured data from `sub`:
             common_analytics::BotEvent {
@@ -239,6 +481,7 @@ impl PluginApi for PluginManager {
                 pi.name == record.name
             }) {
                 let plugin_arc = lock.remove(pos);
+                self.platform_manager.platforms.unregister_owner(&record.name);
                 let _ = plugin_arc.stop().await;
                 tracing::info!("Stopped and removed in-memory plugin '{}'", record.name);
             }
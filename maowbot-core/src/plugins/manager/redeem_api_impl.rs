@@ -37,16 +37,12 @@ impl RedeemApi for PluginManager {
     }
 
     async fn get_usage_for_redeem(&self, redeem_id: Uuid, limit: i64) -> Result<Vec<RedeemUsage>, Error> {
-        let usage_repo = match &self.redeem_usage_repo {
-            repo => repo.clone(),
-        };
+        let usage_repo = self.redeem_usage_repo.clone();
         usage_repo.list_usage_for_redeem(redeem_id, limit).await
     }
 
     async fn get_usage_for_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<RedeemUsage>, Error> {
-        let usage_repo = match &self.redeem_usage_repo {
-            repo => repo.clone(),
-        };
+        let usage_repo = self.redeem_usage_repo.clone();
         usage_repo.list_usage_for_user(user_id, limit).await
     }
 
@@ -69,8 +65,6 @@ impl RedeemApi for PluginManager {
 
 impl PluginManager {
     pub fn resolve_redeem_service(&self) -> Result<Arc<crate::services::RedeemService>, Error> {
-        match &self.redeem_service {
-            svc => Ok(svc.clone()),
-        }
+        Ok(self.redeem_service.clone())
     }
 }
@@ -4,13 +4,11 @@
 use uuid::Uuid;
 use async_trait::async_trait;
 use crate::Error;
-use maowbot_common::models::user::{User};
+use maowbot_common::models::user::User;
 use maowbot_common::models::platform::{PlatformIdentity, Platform};
 use maowbot_common::models::user_analysis::UserAnalysis;
 use maowbot_common::traits::api::UserApi;
 use crate::plugins::manager::core::PluginManager;
-use crate::repositories::postgres::user::UserRepo;
-use crate::repositories::postgres::platform_identity::PlatformIdentityRepo;
 use crate::repositories::postgres::analytics::AnalyticsRepo;
 use crate::repositories::postgres::user_analysis::UserAnalysisRepository;
 
@@ -58,7 +56,7 @@ impl UserApi for PluginManager {
             .filter(|u| {
                 let user_id_str = u.user_id.to_string();
                 let uname = u.global_username.as_deref().unwrap_or("");
-                user_id_str.contains(&query) || uname.to_lowercase().contains(&query.to_lowercase())
+                user_id_str.contains(query) || uname.to_lowercase().contains(&query.to_lowercase())
             })
             .collect();
         Ok(filtered)
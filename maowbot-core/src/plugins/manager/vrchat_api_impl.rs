@@ -5,7 +5,6 @@
 
 use crate::Error;
 use maowbot_common::models::platform::Platform;
-use crate::repositories::postgres::user::UserRepo;
 use crate::platforms::vrchat::client::VRChatClient;
 use maowbot_common::traits::api::{
     VrchatApi, VRChatWorldBasic, VRChatAvatarBasic, VRChatInstanceBasic
@@ -10,6 +10,7 @@ use maowbot_proto::plugs::PluginStreamResponse;
 use maowbot_proto::plugs::plugin_stream_response::Payload as RespPayload;
 
 use maowbot_common::traits::api::BotApi;
+use crate::platforms::registry::PlatformFactory;
 
 
 /// PluginConnectionInfo: in-memory info about a connected plugin.
@@ -44,6 +45,12 @@ pub trait PluginConnection: Send + Sync {
     /// Enable or disable the plugin (the plugin may ignore sends when disabled).
     async fn set_enabled(&self, enable: bool);
 
+    /// Platform integrations this plugin contributes; registered with the
+    /// platform manager when an in-process plugin is loaded.
+    fn platform_factories(&self) -> Vec<Arc<dyn PlatformFactory>> {
+        Vec::new()
+    }
+
     /// If needed, allow downcasting with `as_any()`.
     fn as_any(&self) -> &dyn Any;
 }
@@ -162,6 +169,9 @@ impl PluginConnection for InProcessPluginConnection {
     fn set_bot_api(&self, api: Arc<dyn BotApi>) {
         self.plugin.set_bot_api(api);
     }
+    fn platform_factories(&self) -> Vec<Arc<dyn PlatformFactory>> {
+        self.plugin.platform_factories()
+    }
     async fn set_enabled(&self, enable: bool) {
         let mut guard = self.info.lock().await;
         guard.is_enabled = enable;
@@ -0,0 +1,136 @@
+// File: maowbot-core/src/repositories/memory/bot_config.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+pub use maowbot_common::traits::repository_traits::BotConfigRepository;
+
+use super::WorkspaceRows;
+
+/// (workspace_id, config_key) -> (config_value, config_meta)
+type ConfigRows = HashMap<(Uuid, String), (String, Option<JsonValue>)>;
+
+/// Bot config for one workspace (the default workspace unless `for_workspace` is used).
+/// Secrets are kept apart from the plain rows, unencrypted since nothing is persisted.
+#[derive(Clone)]
+pub struct InMemoryBotConfigRepository {
+    rows: Arc<RwLock<ConfigRows>>,
+    secrets: Arc<RwLock<HashMap<(Uuid, String), String>>>,
+    workspace_id: Uuid,
+}
+
+impl Default for InMemoryBotConfigRepository {
+    fn default() -> Self {
+        Self { rows: Default::default(), secrets: Default::default(), workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+}
+
+impl InMemoryBotConfigRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A repository over the same data that reads and writes `workspace_id`'s config.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { rows: self.rows.clone(), secrets: self.secrets.clone(), workspace_id }
+    }
+
+    pub fn workspace_id(&self) -> Uuid {
+        self.workspace_id
+    }
+
+    fn key(&self, config_key: &str) -> (Uuid, String) {
+        (self.workspace_id, config_key.to_string())
+    }
+}
+
+#[async_trait]
+impl BotConfigRepository for InMemoryBotConfigRepository {
+    async fn get_callback_port(&self) -> Result<Option<u16>, Error> {
+        Ok(self.get_value("callback_port").await?.and_then(|v| v.parse::<u16>().ok()))
+    }
+
+    async fn set_callback_port(&self, port: u16) -> Result<(), Error> {
+        self.set_value("callback_port", &port.to_string()).await
+    }
+
+    async fn set_value(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        self.set_value_kv_meta(config_key, config_value, None).await
+    }
+
+    async fn get_value(&self, config_key: &str) -> Result<Option<String>, Error> {
+        Ok(self.rows.read().get(&self.key(config_key)).map(|(v, _)| v.clone()))
+    }
+
+    async fn list_all(&self) -> Result<Vec<(String, String)>, Error> {
+        Ok(self.rows.read().iter()
+            .filter(|((ws, _), _)| *ws == self.workspace_id)
+            .map(|((_, k), (v, _))| (k.clone(), v.clone()))
+            .collect())
+    }
+
+    async fn delete_value(&self, config_key: &str) -> Result<(), Error> {
+        self.rows.write().remove(&self.key(config_key));
+        self.secrets.write().remove(&self.key(config_key));
+        Ok(())
+    }
+
+    async fn set_value_kv_meta(
+        &self,
+        config_key: &str,
+        config_value: &str,
+        config_meta: Option<JsonValue>
+    ) -> Result<(), Error> {
+        self.secrets.write().remove(&self.key(config_key));
+        self.rows.write().insert(self.key(config_key), (config_value.to_string(), config_meta));
+        Ok(())
+    }
+
+    async fn get_value_kv_meta(
+        &self,
+        config_key: &str,
+        config_value: &str
+    ) -> Result<Option<(String, Option<JsonValue>)>, Error> {
+        Ok(self.rows.read().get(&self.key(config_key))
+            .filter(|(v, _)| v == config_value)
+            .cloned())
+    }
+
+    async fn delete_value_kv(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        let key = self.key(config_key);
+        if rows.get(&key).is_some_and(|(v, _)| v == config_value) {
+            rows.remove(&key);
+        }
+        Ok(())
+    }
+
+    async fn set_secret(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        self.rows.write().remove(&self.key(config_key));
+        self.secrets.write().insert(self.key(config_key), config_value.to_string());
+        Ok(())
+    }
+
+    async fn get_secret(&self, config_key: &str) -> Result<Option<String>, Error> {
+        Ok(self.secrets.read().get(&self.key(config_key)).cloned())
+    }
+
+    async fn list_secret_keys(&self) -> Result<Vec<String>, Error> {
+        Ok(self.secrets.read().keys()
+            .filter(|(ws, _)| *ws == self.workspace_id)
+            .map(|(_, k)| k.clone())
+            .collect())
+    }
+}
+
+impl WorkspaceRows for InMemoryBotConfigRepository {
+    fn remove_workspace_rows(&self, workspace_id: Uuid) {
+        self.rows.write().retain(|(ws, _), _| *ws != workspace_id);
+        self.secrets.write().retain(|(ws, _), _| *ws != workspace_id);
+    }
+}
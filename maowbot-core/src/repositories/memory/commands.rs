@@ -0,0 +1,117 @@
+// File: maowbot-core/src/repositories/memory/commands.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::command::Command;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::CommandRepository;
+
+use super::WorkspaceRows;
+
+/// Commands for one workspace (the default workspace unless `for_workspace` is used).
+#[derive(Clone)]
+pub struct InMemoryCommandRepository {
+    /// command_id -> (workspace_id, command)
+    rows: Arc<RwLock<HashMap<Uuid, (Uuid, Command)>>>,
+    workspace_id: Uuid,
+}
+
+impl Default for InMemoryCommandRepository {
+    fn default() -> Self {
+        Self { rows: Default::default(), workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+}
+
+impl InMemoryCommandRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A repository over the same data scoped to `workspace_id`.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { rows: self.rows.clone(), workspace_id }
+    }
+
+    /// (platform, command_name) is unique per workspace, as in the database schema.
+    fn check_name_free(&self, rows: &HashMap<Uuid, (Uuid, Command)>, cmd: &Command) -> Result<(), Error> {
+        let taken = rows.values().any(|(ws, c)| {
+            *ws == self.workspace_id
+                && c.command_id != cmd.command_id
+                && c.platform == cmd.platform
+                && c.command_name == cmd.command_name
+        });
+        if taken {
+            return Err(Error::ValidationError(format!(
+                "Command '{}' already exists on {}", cmd.command_name, cmd.platform
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommandRepository for InMemoryCommandRepository {
+    async fn create_command(&self, cmd: &Command) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if rows.contains_key(&cmd.command_id) {
+            return Err(Error::ValidationError(format!("Command {} already exists", cmd.command_id)));
+        }
+        self.check_name_free(&rows, cmd)?;
+        rows.insert(cmd.command_id, (self.workspace_id, cmd.clone()));
+        Ok(())
+    }
+
+    async fn get_command_by_id(&self, command_id: Uuid) -> Result<Option<Command>, Error> {
+        Ok(self.rows.read().get(&command_id)
+            .filter(|(ws, _)| *ws == self.workspace_id)
+            .map(|(_, c)| c.clone()))
+    }
+
+    async fn get_command_by_name(&self, platform: &str, command_name: &str) -> Result<Option<Command>, Error> {
+        Ok(self.rows.read().values()
+            .find(|(ws, c)| {
+                *ws == self.workspace_id
+                    && c.platform.eq_ignore_ascii_case(platform)
+                    && c.command_name.eq_ignore_ascii_case(command_name)
+            })
+            .map(|(_, c)| c.clone()))
+    }
+
+    async fn list_commands(&self, platform: &str) -> Result<Vec<Command>, Error> {
+        let mut out: Vec<Command> = self.rows.read().values()
+            .filter(|(ws, c)| *ws == self.workspace_id && c.platform.eq_ignore_ascii_case(platform))
+            .map(|(_, c)| c.clone())
+            .collect();
+        out.sort_by(|a, b| a.command_name.cmp(&b.command_name));
+        Ok(out)
+    }
+
+    async fn update_command(&self, cmd: &Command) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        self.check_name_free(&rows, cmd)?;
+        if let Some((ws, existing)) = rows.get_mut(&cmd.command_id) {
+            if *ws == self.workspace_id {
+                *existing = Command { created_at: existing.created_at, ..cmd.clone() };
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete_command(&self, command_id: Uuid) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if rows.get(&command_id).is_some_and(|(ws, _)| *ws == self.workspace_id) {
+            rows.remove(&command_id);
+        }
+        Ok(())
+    }
+}
+
+impl WorkspaceRows for InMemoryCommandRepository {
+    fn remove_workspace_rows(&self, workspace_id: Uuid) {
+        self.rows.write().retain(|_, (ws, _)| *ws != workspace_id);
+    }
+}
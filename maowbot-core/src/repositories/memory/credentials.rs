@@ -0,0 +1,144 @@
+// File: maowbot-core/src/repositories/memory/credentials.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use parking_lot::RwLock;
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+/// Platform credentials, kept in plain text since they never leave the process.
+/// Sees every workspace unless narrowed with `for_workspace`.
+#[derive(Clone, Default)]
+pub struct InMemoryCredentialsRepository {
+    /// credential_id -> (workspace_id, credential)
+    rows: Arc<RwLock<HashMap<Uuid, (Uuid, PlatformCredential)>>>,
+    /// `None` = all workspaces (new credentials go to the default workspace).
+    workspace_id: Option<Uuid>,
+}
+
+impl InMemoryCredentialsRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A repository over the same data that only sees `workspace_id`'s credentials.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { rows: self.rows.clone(), workspace_id: Some(workspace_id) }
+    }
+
+    fn visible(&self, ws: Uuid) -> bool {
+        self.workspace_id.is_none_or(|own| own == ws)
+    }
+
+    fn find<F>(&self, pred: F) -> Vec<PlatformCredential>
+    where
+        F: Fn(&PlatformCredential) -> bool,
+    {
+        self.rows.read().values()
+            .filter(|(ws, c)| self.visible(*ws) && pred(c))
+            .map(|(_, c)| c.clone())
+            .collect()
+    }
+
+    /// How many credentials `workspace_id` has, whatever this repository sees.
+    pub fn count_in_workspace(&self, workspace_id: Uuid) -> usize {
+        self.rows.read().values().filter(|(ws, _)| *ws == workspace_id).count()
+    }
+
+    /// The id of the visible credential for (platform, user), the table's unique key.
+    fn key_of(&self, rows: &HashMap<Uuid, (Uuid, PlatformCredential)>, platform: &Platform, user_id: Uuid) -> Option<Uuid> {
+        rows.iter()
+            .find(|(_, (ws, c))| self.visible(*ws) && &c.platform == platform && c.user_id == user_id)
+            .map(|(id, _)| *id)
+    }
+}
+
+#[async_trait]
+impl CredentialsRepository for InMemoryCredentialsRepository {
+    async fn store_credentials(&self, creds: &PlatformCredential) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        // Same upsert as the database: an existing (platform, user) row keeps its id, type and creation time
+        let existing = rows.iter()
+            .find(|(_, (_, c))| c.platform == creds.platform && c.user_id == creds.user_id)
+            .map(|(id, (ws, c))| (*id, *ws, c.credential_type.clone(), c.created_at));
+        match existing {
+            Some((id, ws, credential_type, created_at)) => {
+                let creds = PlatformCredential { credential_id: id, credential_type, created_at, ..creds.clone() };
+                rows.insert(id, (ws, creds));
+            }
+            None => {
+                let ws = self.workspace_id.unwrap_or(DEFAULT_WORKSPACE_ID);
+                rows.insert(creds.credential_id, (ws, creds.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_credentials(&self, platform: &Platform, user_id: Uuid) -> Result<Option<PlatformCredential>, Error> {
+        Ok(self.find(|c| &c.platform == platform && c.user_id == user_id).into_iter().next())
+    }
+
+    async fn get_credential_by_id(&self, credential_id: Uuid) -> Result<Option<PlatformCredential>, Error> {
+        Ok(self.find(|c| c.credential_id == credential_id).into_iter().next())
+    }
+
+    async fn update_credentials(&self, creds: &PlatformCredential) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if let Some(id) = self.key_of(&rows, &creds.platform, creds.user_id) {
+            if let Some((_, existing)) = rows.get_mut(&id) {
+                *existing = PlatformCredential {
+                    credential_id: existing.credential_id,
+                    credential_type: existing.credential_type.clone(),
+                    created_at: existing.created_at,
+                    ..creds.clone()
+                };
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete_credentials(&self, platform: &Platform, user_id: Uuid) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if let Some(id) = self.key_of(&rows, platform, user_id) {
+            rows.remove(&id);
+        }
+        Ok(())
+    }
+
+    async fn get_expiring_credentials(&self, within: Duration) -> Result<Vec<PlatformCredential>, Error> {
+        let cutoff = Utc::now() + within;
+        Ok(self.find(|c| c.expires_at.is_some_and(|t| t <= cutoff)))
+    }
+
+    async fn get_all_credentials(&self) -> Result<Vec<PlatformCredential>, Error> {
+        Ok(self.find(|_| true))
+    }
+
+    async fn list_credentials_for_platform(&self, platform: &Platform) -> Result<Vec<PlatformCredential>, Error> {
+        Ok(self.find(|c| &c.platform == platform))
+    }
+
+    async fn get_broadcaster_credential(&self, platform: &Platform) -> Result<Option<PlatformCredential>, Error> {
+        Ok(self.find(|c| &c.platform == platform && c.is_broadcaster).into_iter().next())
+    }
+
+    async fn get_bot_credentials(&self, platform: &Platform) -> Result<Option<PlatformCredential>, Error> {
+        Ok(self.find(|c| &c.platform == platform && c.is_bot).into_iter().next())
+    }
+
+    async fn get_teammate_credentials(&self, platform: &Platform) -> Result<Vec<PlatformCredential>, Error> {
+        Ok(self.find(|c| &c.platform == platform && c.is_teammate))
+    }
+
+    async fn assign_workspace(&self, credential_id: Uuid, workspace_id: Uuid) -> Result<(), Error> {
+        if let Some((ws, _)) = self.rows.write().get_mut(&credential_id) {
+            *ws = workspace_id;
+        }
+        Ok(())
+    }
+}
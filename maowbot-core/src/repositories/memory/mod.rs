@@ -0,0 +1,424 @@
+//! In-memory repositories, for tests and the server's `--memory-db` mode.
+//! Only compiled with the `memory` feature.
+//!
+//! Users, identities, credentials, bot config, commands and redeems have
+//! in-memory doubles that keep the database backends' unique keys and
+//! workspace scoping. `Repositories::memory` puts every other repository on
+//! an in-memory SQLite database (`open_database`), so the full server runs
+//! without a database file or server. Nothing is persisted: the data lives as
+//! long as the process.
+
+pub mod user;
+pub mod platform_identity;
+pub mod credentials;
+pub mod bot_config;
+pub mod commands;
+pub mod redeems;
+pub mod workspaces;
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::crypto::Encryptor;
+use crate::db::sqlite::SqliteDatabase;
+use crate::Error;
+use super::{Repositories, WorkspaceRepos};
+
+pub use bot_config::InMemoryBotConfigRepository;
+pub use commands::InMemoryCommandRepository;
+pub use credentials::InMemoryCredentialsRepository;
+pub use platform_identity::InMemoryPlatformIdentityRepository;
+pub use redeems::InMemoryRedeemRepository;
+pub use user::InMemoryUserRepository;
+pub use workspaces::InMemoryWorkspaceRepository;
+
+/// Rows an in-memory repository keeps per user. The user repository calls
+/// these on merge and delete, as the database's foreign keys would.
+pub trait UserRows: Send + Sync {
+    /// Points `from`'s rows at `to` (a merge of `from` into `to`).
+    fn move_user_rows(&self, from: Uuid, to: Uuid);
+    /// Drops `user_id`'s rows (the user was deleted).
+    fn remove_user_rows(&self, user_id: Uuid);
+}
+
+/// Rows an in-memory repository keeps per workspace. The workspace repository
+/// drops them when the workspace is deleted, as the database's foreign keys would.
+pub trait WorkspaceRows: Send + Sync {
+    fn remove_workspace_rows(&self, workspace_id: Uuid);
+}
+
+/// One shared set of in-memory repositories. Clones see the same data.
+#[derive(Clone)]
+pub struct InMemoryRepositories {
+    pub users: Arc<InMemoryUserRepository>,
+    pub identities: Arc<InMemoryPlatformIdentityRepository>,
+    pub credentials: Arc<InMemoryCredentialsRepository>,
+    pub bot_config: Arc<InMemoryBotConfigRepository>,
+    pub commands: Arc<InMemoryCommandRepository>,
+    pub redeems: Arc<InMemoryRedeemRepository>,
+}
+
+impl Default for InMemoryRepositories {
+    fn default() -> Self {
+        let identities = InMemoryPlatformIdentityRepository::new();
+        Self {
+            users: Arc::new(InMemoryUserRepository::with_identities(identities.clone())),
+            identities: Arc::new(identities),
+            credentials: Default::default(),
+            bot_config: Default::default(),
+            commands: Default::default(),
+            redeems: Default::default(),
+        }
+    }
+}
+
+impl InMemoryRepositories {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Repositories over the same data that only see `workspace_id`'s rows
+    /// (users are global, as in the database backends).
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self {
+            users: self.users.clone(),
+            identities: self.identities.clone(),
+            credentials: Arc::new(self.credentials.for_workspace(workspace_id)),
+            bot_config: Arc::new(self.bot_config.for_workspace(workspace_id)),
+            commands: Arc::new(self.commands.for_workspace(workspace_id)),
+            redeems: Arc::new(self.redeems.for_workspace(workspace_id)),
+        }
+    }
+}
+
+/// A migrated in-memory SQLite database for `Repositories::memory`. Users
+/// live in `InMemoryUserRepository` rather than its `users` table, so its
+/// foreign keys are off.
+pub async fn open_database() -> Result<SqliteDatabase, Error> {
+    let db = SqliteDatabase::in_memory().await?;
+    db.migrate().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(db.pool()).await?;
+    Ok(db)
+}
+
+impl Repositories {
+    /// The in-memory doubles, and `db` (see `open_database`) for every
+    /// repository without one. `workspaces` deletes the doubles' rows along
+    /// with the workspace (`InMemoryWorkspaceRepository`).
+    ///
+    /// `key_rotation` only re-encrypts the secrets in `db`: the doubles keep
+    /// credentials and secret config unencrypted, so they're unaffected by a
+    /// key change and have nothing to rewrite.
+    pub fn memory(db: &SqliteDatabase, encryptor: Encryptor) -> Self {
+        let doubles = InMemoryRepositories::new();
+        let workspaces = InMemoryWorkspaceRepository::new(
+            db.pool().clone(),
+            doubles.credentials.clone(),
+            vec![doubles.bot_config.clone(), doubles.commands.clone(), doubles.redeems.clone()],
+        );
+        Self {
+            users: doubles.users.clone(),
+            identities: doubles.identities.clone(),
+            credentials: doubles.credentials.clone(),
+            bot_config: doubles.bot_config.clone(),
+            commands: doubles.commands.clone(),
+            redeems: doubles.redeems.clone(),
+            workspaces: Arc::new(workspaces),
+            scoped: Arc::new(move |ws| {
+                let scoped = doubles.for_workspace(ws);
+                WorkspaceRepos {
+                    credentials: scoped.credentials,
+                    bot_config: scoped.bot_config,
+                    commands: scoped.commands,
+                    redeems: scoped.redeems,
+                }
+            }),
+            ..Self::sqlite(db, encryptor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use maowbot_common::models::command::Command;
+    use maowbot_common::models::credential::CredentialType;
+    use maowbot_common::models::platform::{Platform, PlatformCredential};
+    use maowbot_common::models::user::User;
+    use maowbot_common::traits::repository_traits::{
+        AnalyticsRepo, BotConfigRepository, CommandRepository, UserAnalysisRepository, UserRepo,
+    };
+
+    fn command(platform: &str, name: &str) -> Command {
+        let now = Utc::now();
+        Command {
+            command_id: Uuid::new_v4(),
+            platform: platform.to_string(),
+            command_name: name.to_string(),
+            min_role: "everyone".to_string(),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            cooldown_seconds: 0,
+            cooldown_warnonce: false,
+            respond_with_credential: None,
+            stream_online_only: false,
+            stream_offline_only: false,
+            active_credential_id: None,
+            user_cooldown_seconds: 0,
+            role_cooldowns: Default::default(),
+            cooldown_bypass_mods: false,
+            reply_privately: false,
+            reply_to_message: false,
+            response: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_users_round_trip_and_names_are_unique() {
+        let repos = InMemoryRepositories::new();
+        let now = Utc::now();
+        let user = User {
+            user_id: Uuid::new_v4(),
+            global_username: Some("Maow".to_string()),
+            created_at: now,
+            last_seen: now,
+            is_active: true,
+        };
+        repos.users.create(&user).await.unwrap();
+        let found = repos.users.get_by_global_username("maow").await.unwrap().unwrap();
+        assert_eq!(found.user_id, user.user_id);
+
+        let twin = User { user_id: Uuid::new_v4(), ..user.clone() };
+        assert!(repos.users.create(&twin).await.is_err());
+
+        repos.users.delete(user.user_id).await.unwrap();
+        assert!(repos.users.get(user.user_id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_workspaces_do_not_see_each_others_rows() {
+        let repos = InMemoryRepositories::new();
+        let other = repos.for_workspace(Uuid::new_v4());
+
+        repos.commands.create_command(&command("twitch-irc", "ping")).await.unwrap();
+        other.commands.create_command(&command("twitch-irc", "ping")).await.unwrap();
+        assert!(repos.commands.create_command(&command("twitch-irc", "ping")).await.is_err());
+        assert_eq!(repos.commands.list_commands("twitch-irc").await.unwrap().len(), 1);
+
+        repos.bot_config.set_value("greeting", "hi").await.unwrap();
+        assert_eq!(repos.bot_config.get_value("greeting").await.unwrap().as_deref(), Some("hi"));
+        assert!(other.bot_config.get_value("greeting").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_kept_out_of_plain_config() {
+        let repos = InMemoryRepositories::new();
+        repos.bot_config.set_secret("webhook.token", "s3cret").await.unwrap();
+        assert_eq!(repos.bot_config.get_secret("webhook.token").await.unwrap().as_deref(), Some("s3cret"));
+        assert!(repos.bot_config.get_value("webhook.token").await.unwrap().is_none());
+        assert!(repos.bot_config.list_all().await.unwrap().is_empty());
+        assert_eq!(repos.bot_config.list_secret_keys().await.unwrap(), vec!["webhook.token"]);
+
+        repos.bot_config.set_value("webhook.token", "plain").await.unwrap();
+        assert!(repos.bot_config.get_secret("webhook.token").await.unwrap().is_none());
+    }
+
+    async fn memory_repos() -> Repositories {
+        let db = open_database().await.unwrap();
+        Repositories::memory(&db, Encryptor::new(&[7u8; 32]).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_user_service_runs_on_memory_repos() {
+        use crate::auth::user_manager::DefaultUserManager;
+        use crate::services::user_service::UserService;
+
+        let repos = memory_repos().await;
+        let manager = Arc::new(DefaultUserManager::new(
+            repos.users.clone(),
+            repos.identities.clone(),
+            repos.user_analysis.clone(),
+        ));
+        let users = UserService::new(manager, repos.identities.clone());
+
+        let user = users.get_or_create_user("twitch-irc", "1234", Some("maow")).await.unwrap();
+        let again = users.get_or_create_user("twitch-irc", "1234", Some("maow")).await.unwrap();
+        assert_eq!(user.user_id, again.user_id);
+        assert_eq!(repos.identities.get_all_for_user(user.user_id).await.unwrap().len(), 1);
+        // The analysis row lives in the SQLite part, keyed by a user it doesn't have
+        assert!(repos.user_analysis.get_analysis(user.user_id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_settings_and_commands_run_on_memory_repos() {
+        use crate::eventbus::EventBus;
+        use crate::settings::SettingsRegistry;
+
+        let repos = memory_repos().await;
+        let settings = SettingsRegistry::new(repos.bot_config.clone(), Arc::new(EventBus::new()));
+        settings.set("ttv_broadcaster_channel", "#maow").await.unwrap();
+
+        let reloaded = SettingsRegistry::new(repos.bot_config.clone(), Arc::new(EventBus::new()));
+        reloaded.load().await.unwrap();
+        assert_eq!(reloaded.get("ttv_broadcaster_channel").as_deref(), Some("#maow"));
+
+        let ws = repos.for_workspace(Uuid::new_v4());
+        ws.commands.create_command(&command("twitch-irc", "hug")).await.unwrap();
+        assert_eq!(ws.commands.list_commands("twitch-irc").await.unwrap().len(), 1);
+        assert!(repos.commands.list_commands("twitch-irc").await.unwrap().is_empty());
+        assert!(ws.bot_config.get_value("ttv_broadcaster_channel").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chat_history_is_kept_for_memory_users() {
+        use maowbot_common::models::analytics::ChatMessage;
+
+        let repos = memory_repos().await;
+        let now = Utc::now();
+        let user = User {
+            user_id: Uuid::new_v4(),
+            global_username: Some("maow".to_string()),
+            created_at: now,
+            last_seen: now,
+            is_active: true,
+        };
+        repos.users.create(&user).await.unwrap();
+        repos.analytics.insert_chat_message(&ChatMessage {
+            message_id: Uuid::new_v4(),
+            platform: "twitch-irc".to_string(),
+            channel: "#maow".to_string(),
+            user_id: user.user_id,
+            message_text: "hi".to_string(),
+            timestamp: now,
+            metadata: None,
+        }).await.unwrap();
+
+        let msgs = repos.analytics
+            .get_messages_for_user(user.user_id, 10, 0, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].message_text, "hi");
+    }
+
+    #[tokio::test]
+    async fn test_deleting_a_workspace_takes_its_rows_from_the_doubles() {
+        use maowbot_common::models::api_token::{ApiRole, ApiToken};
+        use maowbot_common::models::workspace::{Workspace, DEFAULT_WORKSPACE_ID};
+
+        let repos = memory_repos().await;
+        let brand = Workspace {
+            workspace_id: Uuid::new_v4(),
+            name: "brand".to_string(),
+            display_name: None,
+            created_at: Utc::now(),
+        };
+        repos.workspaces.create_workspace(&brand).await.unwrap();
+        repos.workspaces.assign_channel(brand.workspace_id, "twitch-irc", "#Brand").await.unwrap();
+        assert!(repos.workspaces.assign_channel(Uuid::new_v4(), "twitch-irc", "#nowhere").await.is_err());
+        repos.api_tokens.create_token(&ApiToken {
+            token_id: Uuid::new_v4(),
+            name: "brand-bot".to_string(),
+            role: ApiRole::Moderator,
+            token_hash: "00".repeat(32),
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+            workspace_id: Some(brand.workspace_id),
+        }).await.unwrap();
+
+        let ws = repos.for_workspace(brand.workspace_id);
+        ws.commands.create_command(&command("twitch-irc", "hug")).await.unwrap();
+        ws.bot_config.set_value("greeting", "hi").await.unwrap();
+        ws.bot_config.set_secret("webhook.token", "s3cret").await.unwrap();
+        repos.commands.create_command(&command("twitch-irc", "hug")).await.unwrap();
+
+        let now = Utc::now();
+        let cred = PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::TwitchIRC,
+            platform_id: None,
+            credential_type: CredentialType::OAuth2,
+            user_id: Uuid::new_v4(),
+            user_name: "brandbot".to_string(),
+            primary_token: "token".to_string(),
+            refresh_token: None,
+            additional_data: None,
+            expires_at: None,
+            created_at: now,
+            updated_at: now,
+            is_bot: true,
+            is_teammate: false,
+            is_broadcaster: false,
+        };
+        ws.credentials.store_credentials(&cred).await.unwrap();
+        assert!(matches!(
+            repos.workspaces.delete_workspace(brand.workspace_id).await,
+            Err(Error::ValidationError(_))
+        ));
+        assert!(repos.workspaces.get_workspace(brand.workspace_id).await.unwrap().is_some());
+
+        ws.credentials.delete_credentials(&cred.platform, cred.user_id).await.unwrap();
+        repos.workspaces.delete_workspace(brand.workspace_id).await.unwrap();
+        assert!(repos.workspaces.list_channels().await.unwrap().is_empty());
+        assert!(repos.api_tokens.get_token_by_name("brand-bot").await.unwrap().is_none());
+        assert!(ws.commands.list_commands("twitch-irc").await.unwrap().is_empty());
+        assert!(ws.bot_config.get_value("greeting").await.unwrap().is_none());
+        assert!(ws.bot_config.get_secret("webhook.token").await.unwrap().is_none());
+        // Other workspaces keep theirs
+        assert_eq!(repos.commands.list_commands("twitch-irc").await.unwrap().len(), 1);
+        assert!(repos.workspaces.delete_workspace(DEFAULT_WORKSPACE_ID).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_key_rotation_leaves_the_doubles_readable() {
+        use maowbot_common::models::ai::AiCredential;
+
+        let db = open_database().await.unwrap();
+        let encryptor = Encryptor::new(&[7u8; 32]).unwrap();
+        let repos = Repositories::memory(&db, encryptor.clone());
+
+        let now = Utc::now();
+        let cred = PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::Twitch,
+            platform_id: None,
+            credential_type: CredentialType::OAuth2,
+            user_id: Uuid::new_v4(),
+            user_name: "maow".to_string(),
+            primary_token: "token".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            additional_data: None,
+            expires_at: None,
+            created_at: now,
+            updated_at: now,
+            is_bot: false,
+            is_teammate: false,
+            is_broadcaster: true,
+        };
+        repos.credentials.store_credentials(&cred).await.unwrap();
+        repos.bot_config.set_secret("webhook.token", "s3cret").await.unwrap();
+        let ai_key = AiCredential::new(Uuid::new_v4(), "sk-maow", None, true, None);
+        repos.ai_credentials.create_credential(&ai_key).await.unwrap();
+
+        let new_key = [9u8; 32];
+        let mut staged = repos.key_rotation.begin_rotation().await.unwrap();
+        let report = staged
+            .reencrypt_all(&Encryptor::new(&[7u8; 32]).unwrap(), &Encryptor::new(&new_key).unwrap())
+            .await
+            .unwrap();
+        staged.commit().await.unwrap();
+        encryptor.replace_key(&new_key).unwrap();
+
+        // Only the SQLite part holds ciphertext
+        assert_eq!(report.total(), 1);
+        let stored = repos.ai_credentials.get_credential(ai_key.credential_id).await.unwrap().unwrap();
+        assert_eq!(stored.api_key, "sk-maow");
+        let stored = repos.credentials.get_credentials(&Platform::Twitch, cred.user_id).await.unwrap().unwrap();
+        assert_eq!(stored.primary_token, "token");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(repos.bot_config.get_secret("webhook.token").await.unwrap().as_deref(), Some("s3cret"));
+    }
+}
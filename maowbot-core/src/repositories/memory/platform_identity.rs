@@ -0,0 +1,120 @@
+// File: maowbot-core/src/repositories/memory/platform_identity.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::platform::{Platform, PlatformIdentity};
+pub use maowbot_common::traits::repository_traits::PlatformIdentityRepo;
+
+use super::UserRows;
+
+#[derive(Clone, Default)]
+pub struct InMemoryPlatformIdentityRepository {
+    rows: Arc<RwLock<HashMap<Uuid, PlatformIdentity>>>,
+}
+
+impl InMemoryPlatformIdentityRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every identity, for filters that look across users.
+    pub(crate) fn all(&self) -> Vec<PlatformIdentity> {
+        self.rows.read().values().cloned().collect()
+    }
+
+    /// (platform, platform_user_id) is unique, as in the database schema.
+    fn check_free(rows: &HashMap<Uuid, PlatformIdentity>, identity: &PlatformIdentity) -> Result<(), Error> {
+        let taken = rows.values().any(|pi| {
+            pi.platform_identity_id != identity.platform_identity_id
+                && pi.platform == identity.platform
+                && pi.platform_user_id == identity.platform_user_id
+        });
+        if taken {
+            return Err(Error::ValidationError(format!(
+                "{} identity {} already exists", identity.platform, identity.platform_user_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PlatformIdentityRepo for InMemoryPlatformIdentityRepository {
+    async fn create(&self, identity: &PlatformIdentity) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if rows.contains_key(&identity.platform_identity_id) {
+            return Err(Error::ValidationError(format!(
+                "Identity {} already exists", identity.platform_identity_id
+            )));
+        }
+        Self::check_free(&rows, identity)?;
+        rows.insert(identity.platform_identity_id, identity.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<PlatformIdentity>, Error> {
+        Ok(self.rows.read().get(&id).cloned())
+    }
+
+    async fn update(&self, identity: &PlatformIdentity) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        Self::check_free(&rows, identity)?;
+        if let Some(existing) = rows.get_mut(&identity.platform_identity_id) {
+            let created_at = existing.created_at;
+            *existing = PlatformIdentity { created_at, ..identity.clone() };
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Error> {
+        self.rows.write().remove(&id);
+        Ok(())
+    }
+
+    async fn get_by_platform(&self, platform: Platform, platform_user_id: &str)
+                             -> Result<Option<PlatformIdentity>, Error>
+    {
+        Ok(self.rows.read().values()
+            .find(|pi| {
+                pi.platform == platform
+                    && (pi.platform_user_id.eq_ignore_ascii_case(platform_user_id)
+                        || pi.platform_username.eq_ignore_ascii_case(platform_user_id))
+            })
+            .cloned())
+    }
+
+    async fn get_all_for_user(&self, user_id: Uuid) -> Result<Vec<PlatformIdentity>, Error> {
+        let mut found: Vec<PlatformIdentity> = self.rows.read().values()
+            .filter(|pi| pi.user_id == user_id)
+            .cloned()
+            .collect();
+        found.sort_by_key(|pi| pi.created_at);
+        Ok(found)
+    }
+
+    async fn get_by_user_and_platform(
+        &self,
+        user_id: Uuid,
+        platform: &Platform,
+    ) -> Result<Option<PlatformIdentity>, Error> {
+        Ok(self.rows.read().values()
+            .find(|pi| pi.user_id == user_id && pi.platform == *platform)
+            .cloned())
+    }
+}
+
+impl UserRows for InMemoryPlatformIdentityRepository {
+    fn move_user_rows(&self, from: Uuid, to: Uuid) {
+        for pi in self.rows.write().values_mut().filter(|pi| pi.user_id == from) {
+            pi.user_id = to;
+        }
+    }
+
+    fn remove_user_rows(&self, user_id: Uuid) {
+        self.rows.write().retain(|_, pi| pi.user_id != user_id);
+    }
+}
@@ -0,0 +1,117 @@
+// File: maowbot-core/src/repositories/memory/redeems.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::redeem::Redeem;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::RedeemRepository;
+
+use super::WorkspaceRows;
+
+/// Redeems for one workspace (the default workspace unless `for_workspace` is used).
+#[derive(Clone)]
+pub struct InMemoryRedeemRepository {
+    /// redeem_id -> (workspace_id, command)
+    rows: Arc<RwLock<HashMap<Uuid, (Uuid, Redeem)>>>,
+    workspace_id: Uuid,
+}
+
+impl Default for InMemoryRedeemRepository {
+    fn default() -> Self {
+        Self { rows: Default::default(), workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+}
+
+impl InMemoryRedeemRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A repository over the same data scoped to `workspace_id`.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { rows: self.rows.clone(), workspace_id }
+    }
+
+    /// (platform, reward_id) is unique per workspace, as in the database schema.
+    fn check_name_free(&self, rows: &HashMap<Uuid, (Uuid, Redeem)>, rd: &Redeem) -> Result<(), Error> {
+        let taken = rows.values().any(|(ws, r)| {
+            *ws == self.workspace_id
+                && r.redeem_id != rd.redeem_id
+                && r.platform == rd.platform
+                && r.reward_id == rd.reward_id
+        });
+        if taken {
+            return Err(Error::ValidationError(format!(
+                "Reward {} already has a redeem on {}", rd.reward_id, rd.platform
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RedeemRepository for InMemoryRedeemRepository {
+    async fn create_redeem(&self, rd: &Redeem) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if rows.contains_key(&rd.redeem_id) {
+            return Err(Error::ValidationError(format!("Redeem {} already exists", rd.redeem_id)));
+        }
+        self.check_name_free(&rows, rd)?;
+        rows.insert(rd.redeem_id, (self.workspace_id, rd.clone()));
+        Ok(())
+    }
+
+    async fn get_redeem_by_id(&self, redeem_id: Uuid) -> Result<Option<Redeem>, Error> {
+        Ok(self.rows.read().get(&redeem_id)
+            .filter(|(ws, _)| *ws == self.workspace_id)
+            .map(|(_, r)| r.clone()))
+    }
+
+    async fn get_redeem_by_reward_id(&self, platform: &str, reward_id: &str) -> Result<Option<Redeem>, Error> {
+        Ok(self.rows.read().values()
+            .find(|(ws, r)| {
+                *ws == self.workspace_id
+                    && r.platform.eq_ignore_ascii_case(platform)
+                    && r.reward_id.eq_ignore_ascii_case(reward_id)
+            })
+            .map(|(_, r)| r.clone()))
+    }
+
+    async fn list_redeems(&self, platform: &str) -> Result<Vec<Redeem>, Error> {
+        let mut out: Vec<Redeem> = self.rows.read().values()
+            .filter(|(ws, r)| *ws == self.workspace_id && r.platform.eq_ignore_ascii_case(platform))
+            .map(|(_, r)| r.clone())
+            .collect();
+        out.sort_by(|a, b| a.reward_name.cmp(&b.reward_name));
+        Ok(out)
+    }
+
+    async fn update_redeem(&self, rd: &Redeem) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        self.check_name_free(&rows, rd)?;
+        if let Some((ws, existing)) = rows.get_mut(&rd.redeem_id) {
+            if *ws == self.workspace_id {
+                *existing = Redeem { created_at: existing.created_at, ..rd.clone() };
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete_redeem(&self, redeem_id: Uuid) -> Result<(), Error> {
+        let mut rows = self.rows.write();
+        if rows.get(&redeem_id).is_some_and(|(ws, _)| *ws == self.workspace_id) {
+            rows.remove(&redeem_id);
+        }
+        Ok(())
+    }
+}
+
+impl WorkspaceRows for InMemoryRedeemRepository {
+    fn remove_workspace_rows(&self, workspace_id: Uuid) {
+        self.rows.write().retain(|_, (ws, _)| *ws != workspace_id);
+    }
+}
@@ -0,0 +1,176 @@
+// File: maowbot-core/src/repositories/memory/user.rs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::user::{User, UserListQuery, UserSort};
+pub use maowbot_common::traits::repository_traits::UserRepo;
+
+use super::platform_identity::InMemoryPlatformIdentityRepository;
+use super::UserRows;
+
+/// Users, plus the identities their listing filters look at. Repositories
+/// registered with `add_dependent` follow deletes and merges, like the
+/// database's foreign keys.
+#[derive(Clone)]
+pub struct InMemoryUserRepository {
+    users: Arc<RwLock<HashMap<Uuid, User>>>,
+    identities: InMemoryPlatformIdentityRepository,
+    dependents: Arc<RwLock<Vec<Arc<dyn UserRows>>>>,
+}
+
+impl Default for InMemoryUserRepository {
+    fn default() -> Self {
+        Self::with_identities(InMemoryPlatformIdentityRepository::new())
+    }
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_identities(identities: InMemoryPlatformIdentityRepository) -> Self {
+        let repo = Self {
+            users: Default::default(),
+            identities: identities.clone(),
+            dependents: Default::default(),
+        };
+        repo.add_dependent(Arc::new(identities));
+        repo
+    }
+
+    /// Rows keyed by user that should be moved on merge and dropped on delete.
+    pub fn add_dependent(&self, rows: Arc<dyn UserRows>) {
+        self.dependents.write().push(rows);
+    }
+
+    fn matches(&self, user: &User, query: &UserListQuery) -> bool {
+        if query.active_only && !user.is_active {
+            return false;
+        }
+        if let Some(prefix) = query.name_prefix.as_deref().filter(|p| !p.is_empty()) {
+            let name = user.global_username.as_deref().unwrap_or_default().to_lowercase();
+            if !name.starts_with(&prefix.to_lowercase()) {
+                return false;
+            }
+        }
+        if query.platforms.is_empty() && query.roles.is_empty() {
+            return true;
+        }
+        let identities: Vec<_> = self.identities.all().into_iter()
+            .filter(|pi| pi.user_id == user.user_id)
+            .collect();
+        let on_platform = query.platforms.is_empty() || identities.iter().any(|pi| {
+            query.platforms.iter().any(|p| p.eq_ignore_ascii_case(&pi.platform.to_string()))
+        });
+        let has_role = query.roles.is_empty() || identities.iter().any(|pi| {
+            pi.platform_roles.iter().any(|r| query.roles.contains(r))
+        });
+        on_platform && has_role
+    }
+
+    /// `global_username` is unique (case-insensitively), as in the database schema.
+    fn check_name_free(users: &HashMap<Uuid, User>, user: &User) -> Result<(), Error> {
+        let Some(name) = &user.global_username else { return Ok(()) };
+        let taken = users.values().any(|u| {
+            u.user_id != user.user_id
+                && u.global_username.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name))
+        });
+        if taken {
+            return Err(Error::ValidationError(format!("Username '{}' is already taken", name)));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserRepo for InMemoryUserRepository {
+    async fn create(&self, user: &User) -> Result<(), Error> {
+        let mut users = self.users.write();
+        if users.contains_key(&user.user_id) {
+            return Err(Error::ValidationError(format!("User {} already exists", user.user_id)));
+        }
+        Self::check_name_free(&users, user)?;
+        users.insert(user.user_id, user.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<User>, Error> {
+        Ok(self.users.read().get(&id).cloned())
+    }
+
+    async fn get_by_global_username(&self, name: &str) -> Result<Option<User>, Error> {
+        Ok(self.users.read().values()
+            .find(|u| u.global_username.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+            .cloned())
+    }
+
+    async fn update(&self, user: &User) -> Result<(), Error> {
+        let mut users = self.users.write();
+        Self::check_name_free(&users, user)?;
+        if let Some(existing) = users.get_mut(&user.user_id) {
+            existing.global_username = user.global_username.clone();
+            existing.last_seen = user.last_seen;
+            existing.is_active = user.is_active;
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Error> {
+        self.users.write().remove(&id);
+        for rows in self.dependents.read().iter() {
+            rows.remove_user_rows(id);
+        }
+        Ok(())
+    }
+
+    async fn list_all(&self) -> Result<Vec<User>, Error> {
+        let mut all: Vec<User> = self.users.read().values().cloned().collect();
+        all.sort_by_key(|u| u.created_at);
+        Ok(all)
+    }
+
+    async fn list_page(&self, query: &UserListQuery) -> Result<(Vec<User>, i64), Error> {
+        let mut found: Vec<User> = self.users.read().values()
+            .filter(|u| self.matches(u, query))
+            .cloned()
+            .collect();
+        // user_id breaks ties so pages don't overlap or skip rows
+        found.sort_by(|a, b| {
+            let order = match query.sort {
+                UserSort::CreatedAt => a.created_at.cmp(&b.created_at),
+                UserSort::LastSeen => a.last_seen.cmp(&b.last_seen),
+                UserSort::Username => {
+                    let name = |u: &User| u.global_username.as_ref().map(|n| n.to_lowercase());
+                    match (name(a), name(b)) {
+                        // Users without a name go last in either direction
+                        (x, y) if x.is_none() != y.is_none() => return x.is_none().cmp(&y.is_none()),
+                        (x, y) => x.cmp(&y),
+                    }
+                }
+            };
+            let order = order.then(a.user_id.cmp(&b.user_id));
+            if query.descending { order.reverse() } else { order }
+        });
+        let total = found.len() as i64;
+        let page = found.into_iter()
+            .skip(query.offset.max(0) as usize)
+            .take(query.limit.max(1) as usize)
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn merge_users(&self, primary_user_id: Uuid, duplicate_user_ids: Vec<Uuid>) -> Result<(), Error> {
+        for dup_id in duplicate_user_ids {
+            for rows in self.dependents.read().iter() {
+                rows.move_user_rows(dup_id, primary_user_id);
+            }
+            self.users.write().remove(&dup_id);
+        }
+        Ok(())
+    }
+}
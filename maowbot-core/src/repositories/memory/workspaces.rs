@@ -0,0 +1,100 @@
+// File: maowbot-core/src/repositories/memory/workspaces.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::workspace::{Workspace, WorkspaceChannel, DEFAULT_WORKSPACE_ID};
+use maowbot_common::traits::repository_traits::WorkspaceRepository;
+
+use crate::repositories::sqlite::workspaces::SqliteWorkspaceRepository;
+use super::{InMemoryCredentialsRepository, WorkspaceRows};
+
+/// Workspaces for `Repositories::memory`: the rows live in the in-memory
+/// SQLite database, whose foreign keys are off (see `open_database`), and
+/// most of what a workspace owns lives in the doubles. Deleting a workspace
+/// does what the foreign keys would: it's refused while the credentials
+/// double still has the workspace's credentials, and otherwise takes its
+/// channel routes, API tokens and the doubles' rows with it. Routes can
+/// only point at workspaces that exist.
+pub struct InMemoryWorkspaceRepository {
+    inner: SqliteWorkspaceRepository,
+    pool: Pool<Sqlite>,
+    credentials: Arc<InMemoryCredentialsRepository>,
+    dependents: Vec<Arc<dyn WorkspaceRows>>,
+}
+
+impl InMemoryWorkspaceRepository {
+    pub fn new(
+        pool: Pool<Sqlite>,
+        credentials: Arc<InMemoryCredentialsRepository>,
+        dependents: Vec<Arc<dyn WorkspaceRows>>,
+    ) -> Self {
+        Self { inner: SqliteWorkspaceRepository::new(pool.clone()), pool, credentials, dependents }
+    }
+}
+
+#[async_trait]
+impl WorkspaceRepository for InMemoryWorkspaceRepository {
+    async fn create_workspace(&self, ws: &Workspace) -> Result<(), Error> {
+        self.inner.create_workspace(ws).await
+    }
+
+    async fn get_workspace(&self, workspace_id: Uuid) -> Result<Option<Workspace>, Error> {
+        self.inner.get_workspace(workspace_id).await
+    }
+
+    async fn get_workspace_by_name(&self, name: &str) -> Result<Option<Workspace>, Error> {
+        self.inner.get_workspace_by_name(name).await
+    }
+
+    async fn list_workspaces(&self) -> Result<Vec<Workspace>, Error> {
+        self.inner.list_workspaces().await
+    }
+
+    async fn delete_workspace(&self, workspace_id: Uuid) -> Result<(), Error> {
+        if workspace_id != DEFAULT_WORKSPACE_ID {
+            // The RESTRICT foreign key, for credentials the database never sees
+            let credentials = self.credentials.count_in_workspace(workspace_id);
+            if credentials > 0 {
+                return Err(Error::ValidationError(format!(
+                    "The workspace still has {} credential(s); remove them or move them to another workspace first",
+                    credentials
+                )));
+            }
+        }
+        self.inner.delete_workspace(workspace_id).await?;
+
+        // The CASCADE foreign keys
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM workspace_channels WHERE workspace_id = ?1")
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM api_tokens WHERE workspace_id = ?1")
+            .bind(workspace_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        for rows in &self.dependents {
+            rows.remove_workspace_rows(workspace_id);
+        }
+        Ok(())
+    }
+
+    async fn assign_channel(&self, workspace_id: Uuid, platform: &str, channel: &str) -> Result<(), Error> {
+        if self.inner.get_workspace(workspace_id).await?.is_none() {
+            return Err(Error::ValidationError(format!("Workspace {} does not exist", workspace_id)));
+        }
+        self.inner.assign_channel(workspace_id, platform, channel).await
+    }
+
+    async fn unassign_channel(&self, platform: &str, channel: &str) -> Result<bool, Error> {
+        self.inner.unassign_channel(platform, channel).await
+    }
+
+    async fn list_channels(&self) -> Result<Vec<WorkspaceChannel>, Error> {
+        self.inner.list_channels().await
+    }
+}
@@ -1,2 +1,107 @@
 // src/repositories/mod.rs
-pub mod postgres;
\ No newline at end of file
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "memory")]
+pub mod memory;
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use maowbot_common::traits::event_pipeline_traits::{
+    EventPipelineRepository, EventPipelineSystemRepository, PipelineExecutionLogRepository,
+};
+use maowbot_common::traits::osc_toggle_traits::OscToggleRepository;
+use maowbot_common::traits::repository_traits::*;
+
+use crate::crypto::rotation::KeyRotationRepository;
+use crate::repositories::postgres::autostart::AutostartRepository;
+use crate::services::data_export::ExportRepository;
+use crate::services::ui_events::UiEventJournal;
+use crate::tasks::analysis_recompute::AnalysisRunRepository;
+use crate::tasks::retention::RetentionRepository;
+
+/// Repositories that are scoped to one workspace.
+pub struct WorkspaceRepos {
+    pub credentials: Arc<dyn CredentialsRepository + Send + Sync>,
+    pub bot_config: Arc<dyn BotConfigRepository + Send + Sync>,
+    pub commands: Arc<dyn CommandRepository + Send + Sync>,
+    pub redeems: Arc<dyn RedeemRepository + Send + Sync>,
+}
+
+/// Every repository the server runs on, built over one backend
+/// (`Repositories::postgres`, `::sqlite` or `::memory`). `credentials` sees
+/// every workspace; `bot_config`, `commands` and `redeems` are the default
+/// workspace's. `for_workspace` gives another workspace's.
+#[derive(Clone)]
+pub struct Repositories {
+    pub users: Arc<dyn UserRepo + Send + Sync>,
+    pub identities: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+    pub user_analysis: Arc<dyn UserAnalysisRepository>,
+    pub user_notes: Arc<dyn UserNotesRepository>,
+    pub workspaces: Arc<dyn WorkspaceRepository>,
+    pub api_tokens: Arc<dyn ApiTokenRepository>,
+    pub credentials: Arc<dyn CredentialsRepository + Send + Sync>,
+    pub platform_configs: Arc<dyn PlatformConfigRepository + Send + Sync>,
+    pub bot_config: Arc<dyn BotConfigRepository + Send + Sync>,
+    pub autostart: Arc<dyn AutostartRepository + Send + Sync>,
+
+    pub analytics: Arc<dyn AnalyticsRepo>,
+    pub chat_archive: Arc<dyn ChatArchiveRepository>,
+    pub commands: Arc<dyn CommandRepository + Send + Sync>,
+    pub command_usage: Arc<dyn CommandUsageRepository + Send + Sync>,
+    pub redeems: Arc<dyn RedeemRepository + Send + Sync>,
+    pub redeem_usage: Arc<dyn RedeemUsageRepository + Send + Sync>,
+    pub redeem_schedules: Arc<dyn RedeemScheduleRepository>,
+    pub redeem_approvals: Arc<dyn RedeemApprovalRepository + Send + Sync>,
+
+    pub discord: Arc<dyn DiscordRepository + Send + Sync>,
+    pub obs: Arc<dyn ObsRepository>,
+    pub drip: Arc<dyn DripRepository>,
+    pub osc_toggles: Arc<dyn OscToggleRepository + Send + Sync>,
+    pub midi_mappings: Arc<dyn MidiMappingRepository + Send + Sync>,
+
+    /// One pipeline store, seen through the traits its users need.
+    pub pipelines: Arc<dyn EventPipelineRepository>,
+    pub pipeline_logs: Arc<dyn PipelineExecutionLogRepository>,
+    pub pipeline_system: Arc<dyn EventPipelineSystemRepository>,
+
+    pub ai_providers: Arc<dyn AiProviderRepository + Send + Sync>,
+    pub ai_credentials: Arc<dyn AiCredentialRepository + Send + Sync>,
+    pub ai_models: Arc<dyn AiModelRepository + Send + Sync>,
+    pub ai_triggers: Arc<dyn AiTriggerRepository + Send + Sync>,
+    pub ai_memories: Arc<dyn AiMemoryRepository + Send + Sync>,
+    pub ai_agents: Arc<dyn AiAgentRepository + Send + Sync>,
+    pub ai_actions: Arc<dyn AiActionRepository + Send + Sync>,
+    pub ai_prompts: Arc<dyn AiSystemPromptRepository + Send + Sync>,
+    pub ai_configurations: Arc<dyn AiConfigurationRepository + Send + Sync>,
+
+    pub donations: Arc<dyn DonationRepository>,
+    pub stream_markers: Arc<dyn StreamMarkerRepository>,
+    pub stream_sessions: Arc<dyn StreamSessionRepository>,
+    pub giveaways: Arc<dyn GiveawayRepository>,
+    pub protection_incidents: Arc<dyn ProtectionIncidentRepository>,
+    pub moderation_rules: Arc<dyn ModerationRuleRepository>,
+    pub emote_stats: Arc<dyn EmoteStatsRepository>,
+    pub clips: Arc<dyn ClipRepository>,
+    pub localization: Arc<dyn LocalizationRepository>,
+    pub watchtime: Arc<dyn WatchtimeRepository>,
+    pub responders: Arc<dyn ResponderRepository>,
+    pub alert_rules: Arc<dyn AlertRuleRepository>,
+    pub milestones: Arc<dyn MilestoneRepository>,
+
+    pub retention: Arc<dyn RetentionRepository>,
+    pub ui_journal: Arc<dyn UiEventJournal>,
+    pub analysis_runs: Arc<dyn AnalysisRunRepository>,
+    pub exports: Arc<dyn ExportRepository>,
+    pub key_rotation: Arc<dyn KeyRotationRepository>,
+
+    scoped: Arc<dyn Fn(Uuid) -> WorkspaceRepos + Send + Sync>,
+}
+
+impl Repositories {
+    /// Credentials, bot config, commands and redeems that only see `workspace_id`'s rows.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> WorkspaceRepos {
+        (self.scoped)(workspace_id)
+    }
+}
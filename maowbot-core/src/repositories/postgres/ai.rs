@@ -37,12 +37,12 @@ impl AiProviderRepository for PostgresAiProviderRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6)
             "#,
         )
-        .bind(&provider.provider_id)
+        .bind(provider.provider_id)
         .bind(&provider.name)
         .bind(&provider.description)
-        .bind(&provider.enabled)
-        .bind(&provider.created_at)
-        .bind(&provider.updated_at)
+        .bind(provider.enabled)
+        .bind(provider.created_at)
+        .bind(provider.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -58,7 +58,7 @@ impl AiProviderRepository for PostgresAiProviderRepository {
             WHERE provider_id = $1
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -102,10 +102,10 @@ impl AiProviderRepository for PostgresAiProviderRepository {
             WHERE provider_id = $1
             "#,
         )
-        .bind(&provider.provider_id)
+        .bind(provider.provider_id)
         .bind(&provider.name)
         .bind(&provider.description)
-        .bind(&provider.enabled)
+        .bind(provider.enabled)
         .bind(Utc::now())
         .execute(&self.pool)
         .await?;
@@ -120,7 +120,7 @@ impl AiProviderRepository for PostgresAiProviderRepository {
             WHERE provider_id = $1
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .execute(&self.pool)
         .await?;
 
@@ -154,6 +154,7 @@ impl PostgresAiCredentialRepository {
 #[async_trait]
 impl AiCredentialRepository for PostgresAiCredentialRepository {
     async fn create_credential(&self, credential: &AiCredential) -> Result<(), Error> {
+        let _write = self.encryptor.write_guard().await;
         let encrypted = self.encrypt_credentials(credential).await?;
 
         query(
@@ -164,14 +165,14 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
         )
-        .bind(&encrypted.credential_id)
-        .bind(&encrypted.provider_id)
+        .bind(encrypted.credential_id)
+        .bind(encrypted.provider_id)
         .bind(&encrypted.api_key)
         .bind(&encrypted.api_base)
-        .bind(&encrypted.is_default)
+        .bind(encrypted.is_default)
         .bind(&encrypted.additional_data)
-        .bind(&encrypted.created_at)
-        .bind(&encrypted.updated_at)
+        .bind(encrypted.created_at)
+        .bind(encrypted.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -188,7 +189,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE credential_id = $1
             "#,
         )
-        .bind(&credential_id)
+        .bind(credential_id)
         .fetch_optional(&self.pool)
         .await?;
 
@@ -210,7 +211,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             ORDER BY created_at DESC
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .fetch_all(&self.pool)
         .await?;
 
@@ -253,7 +254,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE provider_id = $1 AND is_default = true
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .fetch_optional(&self.pool)
         .await?;
 
@@ -265,6 +266,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
     }
 
     async fn update_credential(&self, credential: &AiCredential) -> Result<(), Error> {
+        let _write = self.encryptor.write_guard().await;
         let encrypted = self.encrypt_credentials(credential).await?;
 
         query(
@@ -279,10 +281,10 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE credential_id = $1
             "#,
         )
-        .bind(&encrypted.credential_id)
+        .bind(encrypted.credential_id)
         .bind(&encrypted.api_key)
         .bind(&encrypted.api_base)
-        .bind(&encrypted.is_default)
+        .bind(encrypted.is_default)
         .bind(&encrypted.additional_data)
         .bind(Utc::now())
         .execute(&self.pool)
@@ -303,7 +305,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE credential_id = $1
             "#,
         )
-        .bind(&credential_id)
+        .bind(credential_id)
         .fetch_one(&mut *tx)
         .await?
         .get(0);
@@ -316,7 +318,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE provider_id = $1 AND is_default = true
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .bind(Utc::now())
         .execute(&mut *tx)
         .await?;
@@ -329,7 +331,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE credential_id = $1
             "#,
         )
-        .bind(&credential_id)
+        .bind(credential_id)
         .bind(Utc::now())
         .execute(&mut *tx)
         .await?;
@@ -347,7 +349,7 @@ impl AiCredentialRepository for PostgresAiCredentialRepository {
             WHERE credential_id = $1
             "#,
         )
-        .bind(&credential_id)
+        .bind(credential_id)
         .execute(&self.pool)
         .await?;
 
@@ -376,14 +378,14 @@ impl AiModelRepository for PostgresAiModelRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
         )
-        .bind(&model.model_id)
-        .bind(&model.provider_id)
+        .bind(model.model_id)
+        .bind(model.provider_id)
         .bind(&model.name)
         .bind(&model.description)
-        .bind(&model.is_default)
+        .bind(model.is_default)
         .bind(&model.capabilities)
-        .bind(&model.created_at)
-        .bind(&model.updated_at)
+        .bind(model.created_at)
+        .bind(model.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -400,7 +402,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE model_id = $1
             "#,
         )
-        .bind(&model_id)
+        .bind(model_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -415,7 +417,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE provider_id = $1 AND LOWER(name) = LOWER($2)
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .bind(name)
         .fetch_optional(&self.pool)
         .await?)
@@ -432,7 +434,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             ORDER BY name
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .fetch_all(&self.pool)
         .await?)
     }
@@ -447,7 +449,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE provider_id = $1 AND is_default = true
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -465,10 +467,10 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE model_id = $1
             "#,
         )
-        .bind(&model.model_id)
+        .bind(model.model_id)
         .bind(&model.name)
         .bind(&model.description)
-        .bind(&model.is_default)
+        .bind(model.is_default)
         .bind(&model.capabilities)
         .bind(Utc::now())
         .execute(&self.pool)
@@ -489,7 +491,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE model_id = $1
             "#,
         )
-        .bind(&model_id)
+        .bind(model_id)
         .fetch_one(&mut *tx)
         .await?
         .get(0);
@@ -502,7 +504,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE provider_id = $1 AND is_default = true
             "#,
         )
-        .bind(&provider_id)
+        .bind(provider_id)
         .bind(Utc::now())
         .execute(&mut *tx)
         .await?;
@@ -515,7 +517,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE model_id = $1
             "#,
         )
-        .bind(&model_id)
+        .bind(model_id)
         .bind(Utc::now())
         .execute(&mut *tx)
         .await?;
@@ -533,7 +535,7 @@ impl AiModelRepository for PostgresAiModelRepository {
             WHERE model_id = $1
             "#,
         )
-        .bind(&model_id)
+        .bind(model_id)
         .execute(&self.pool)
         .await?;
 
@@ -563,19 +565,19 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             "#,
         )
-        .bind(&trigger.trigger_id)
+        .bind(trigger.trigger_id)
         .bind(&trigger.trigger_type)
         .bind(&trigger.pattern)
-        .bind(&trigger.model_id)
-        .bind(&trigger.agent_id)
+        .bind(trigger.model_id)
+        .bind(trigger.agent_id)
         .bind(&trigger.system_prompt)
         .bind(&trigger.platform)
         .bind(&trigger.channel)
         .bind(&trigger.schedule)
         .bind(&trigger.condition)
-        .bind(&trigger.enabled)
-        .bind(&trigger.created_at)
-        .bind(&trigger.updated_at)
+        .bind(trigger.enabled)
+        .bind(trigger.created_at)
+        .bind(trigger.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -593,7 +595,7 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             WHERE trigger_id = $1
             "#,
         )
-        .bind(&trigger_id)
+        .bind(trigger_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -641,7 +643,7 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             ORDER BY pattern
             "#,
         )
-        .bind(&model_id)
+        .bind(model_id)
         .fetch_all(&self.pool)
         .await?)
     }
@@ -658,7 +660,7 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             ORDER BY pattern
             "#,
         )
-        .bind(&agent_id)
+        .bind(agent_id)
         .fetch_all(&self.pool)
         .await?)
     }
@@ -764,51 +766,39 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             };
             
             // Create model if model fields are present
-            let model = if jt.model_id.is_some() {
-                Some(AiModel {
-                    model_id: jt.model_id.unwrap(),
-                    provider_id: jt.provider_id.unwrap_or_else(Uuid::nil),
-                    name: jt.model_name.unwrap_or_default(),
-                    description: jt.model_description,
-                    is_default: jt.model_is_default.unwrap_or(false),
-                    capabilities: jt.model_capabilities.map(|j| j.0),
-                    created_at: jt.model_created_at.unwrap_or_else(Utc::now),
-                    updated_at: jt.model_updated_at.unwrap_or_else(Utc::now),
-                })
-            } else {
-                None
-            };
+            let model = jt.model_id.map(|model_id| AiModel {
+                model_id,
+                provider_id: jt.provider_id.unwrap_or_else(Uuid::nil),
+                name: jt.model_name.unwrap_or_default(),
+                description: jt.model_description,
+                is_default: jt.model_is_default.unwrap_or(false),
+                capabilities: jt.model_capabilities.map(|j| j.0),
+                created_at: jt.model_created_at.unwrap_or_else(Utc::now),
+                updated_at: jt.model_updated_at.unwrap_or_else(Utc::now),
+            });
             
             // Create provider if provider fields are present
-            let provider = if jt.provider_id.is_some() {
-                Some(AiProvider {
-                    provider_id: jt.provider_id.unwrap(),
-                    name: jt.provider_name.unwrap_or_default(),
-                    description: jt.provider_description,
-                    enabled: jt.provider_enabled.unwrap_or(true),
-                    created_at: jt.provider_created_at.unwrap_or_else(Utc::now),
-                    updated_at: jt.provider_updated_at.unwrap_or_else(Utc::now),
-                })
-            } else {
-                None
-            };
+            let provider = jt.provider_id.map(|provider_id| AiProvider {
+                provider_id,
+                name: jt.provider_name.unwrap_or_default(),
+                description: jt.provider_description,
+                enabled: jt.provider_enabled.unwrap_or(true),
+                created_at: jt.provider_created_at.unwrap_or_else(Utc::now),
+                updated_at: jt.provider_updated_at.unwrap_or_else(Utc::now),
+            });
             
             // Create agent if agent fields are present
-            let agent = if jt.agent_id.is_some() {
-                Some(AiAgent {
-                    agent_id: jt.agent_id.unwrap(),
-                    name: jt.agent_name.unwrap_or_default(),
-                    description: jt.agent_description,
-                    model_id: jt.model_id.unwrap_or_else(Uuid::nil),
-                    system_prompt: jt.agent_system_prompt,
-                    capabilities: jt.agent_capabilities.map(|j| j.0),
-                    enabled: jt.agent_enabled.unwrap_or(true),
-                    created_at: jt.agent_created_at.unwrap_or_else(Utc::now),
-                    updated_at: jt.agent_updated_at.unwrap_or_else(Utc::now),
-                })
-            } else {
-                None
-            };
+            let agent = jt.agent_id.map(|agent_id| AiAgent {
+                agent_id,
+                name: jt.agent_name.unwrap_or_default(),
+                description: jt.agent_description,
+                model_id: jt.model_id.unwrap_or_else(Uuid::nil),
+                system_prompt: jt.agent_system_prompt,
+                capabilities: jt.agent_capabilities.map(|j| j.0),
+                enabled: jt.agent_enabled.unwrap_or(true),
+                created_at: jt.agent_created_at.unwrap_or_else(Utc::now),
+                updated_at: jt.agent_updated_at.unwrap_or_else(Utc::now),
+            });
             
             AiTriggerWithDetails {
                 trigger,
@@ -840,17 +830,17 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             WHERE trigger_id = $1
             "#,
         )
-        .bind(&trigger.trigger_id)
+        .bind(trigger.trigger_id)
         .bind(&trigger.trigger_type)
         .bind(&trigger.pattern)
-        .bind(&trigger.model_id)
-        .bind(&trigger.agent_id)
+        .bind(trigger.model_id)
+        .bind(trigger.agent_id)
         .bind(&trigger.system_prompt)
         .bind(&trigger.platform)
         .bind(&trigger.channel)
         .bind(&trigger.schedule)
         .bind(&trigger.condition)
-        .bind(&trigger.enabled)
+        .bind(trigger.enabled)
         .bind(Utc::now())
         .execute(&self.pool)
         .await?;
@@ -865,7 +855,7 @@ impl AiTriggerRepository for PostgresAiTriggerRepository {
             WHERE trigger_id = $1
             "#,
         )
-        .bind(&trigger_id)
+        .bind(trigger_id)
         .execute(&self.pool)
         .await?;
 
@@ -893,12 +883,12 @@ impl AiMemoryRepository for PostgresAiMemoryRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
-        .bind(&memory.memory_id)
-        .bind(&memory.user_id)
+        .bind(memory.memory_id)
+        .bind(memory.user_id)
         .bind(&memory.platform)
         .bind(&memory.role)
         .bind(&memory.content)
-        .bind(&memory.timestamp)
+        .bind(memory.timestamp)
         .bind(&memory.metadata)
         .execute(&self.pool)
         .await?;
@@ -915,7 +905,7 @@ impl AiMemoryRepository for PostgresAiMemoryRepository {
             WHERE memory_id = $1
             "#,
         )
-        .bind(&memory_id)
+        .bind(memory_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -931,7 +921,7 @@ impl AiMemoryRepository for PostgresAiMemoryRepository {
             LIMIT $2
             "#,
         )
-        .bind(&user_id)
+        .bind(user_id)
         .bind(limit)
         .fetch_all(&self.pool)
         .await?)
@@ -944,7 +934,7 @@ impl AiMemoryRepository for PostgresAiMemoryRepository {
             WHERE memory_id = $1
             "#,
         )
-        .bind(&memory_id)
+        .bind(memory_id)
         .execute(&self.pool)
         .await?;
 
@@ -958,7 +948,7 @@ impl AiMemoryRepository for PostgresAiMemoryRepository {
             WHERE user_id = $1
             "#,
         )
-        .bind(&user_id)
+        .bind(user_id)
         .execute(&self.pool)
         .await?;
 
@@ -972,7 +962,7 @@ impl AiMemoryRepository for PostgresAiMemoryRepository {
             WHERE timestamp < $1
             "#,
         )
-        .bind(&older_than)
+        .bind(older_than)
         .execute(&self.pool)
         .await?;
 
@@ -1001,15 +991,15 @@ impl AiAgentRepository for PostgresAiAgentRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             "#,
         )
-        .bind(&agent.agent_id)
+        .bind(agent.agent_id)
         .bind(&agent.name)
         .bind(&agent.description)
-        .bind(&agent.model_id)
+        .bind(agent.model_id)
         .bind(&agent.system_prompt)
         .bind(&agent.capabilities)
-        .bind(&agent.enabled)
-        .bind(&agent.created_at)
-        .bind(&agent.updated_at)
+        .bind(agent.enabled)
+        .bind(agent.created_at)
+        .bind(agent.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -1026,7 +1016,7 @@ impl AiAgentRepository for PostgresAiAgentRepository {
             WHERE agent_id = $1
             "#,
         )
-        .bind(&agent_id)
+        .bind(agent_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -1075,7 +1065,7 @@ impl AiAgentRepository for PostgresAiAgentRepository {
                 WHERE model_id = $1
                 "#,
             )
-            .bind(&agent.model_id)
+            .bind(agent.model_id)
             .fetch_optional(&self.pool)
             .await?;
             
@@ -1089,7 +1079,7 @@ impl AiAgentRepository for PostgresAiAgentRepository {
                     WHERE provider_id = $1
                     "#,
                 )
-                .bind(&model.provider_id)
+                .bind(model.provider_id)
                 .fetch_optional(&self.pool)
                 .await?;
                 
@@ -1105,7 +1095,7 @@ impl AiAgentRepository for PostgresAiAgentRepository {
                         WHERE agent_id = $1
                         "#,
                     )
-                    .bind(&agent.agent_id)
+                    .bind(agent.agent_id)
                     .fetch_all(&self.pool)
                     .await?;
                     
@@ -1137,13 +1127,13 @@ impl AiAgentRepository for PostgresAiAgentRepository {
             WHERE agent_id = $1
             "#,
         )
-        .bind(&agent.agent_id)
+        .bind(agent.agent_id)
         .bind(&agent.name)
         .bind(&agent.description)
-        .bind(&agent.model_id)
+        .bind(agent.model_id)
         .bind(&agent.system_prompt)
         .bind(&agent.capabilities)
-        .bind(&agent.enabled)
+        .bind(agent.enabled)
         .bind(Utc::now())
         .execute(&self.pool)
         .await?;
@@ -1158,7 +1148,7 @@ impl AiAgentRepository for PostgresAiAgentRepository {
             WHERE agent_id = $1
             "#,
         )
-        .bind(&agent_id)
+        .bind(agent_id)
         .execute(&self.pool)
         .await?;
 
@@ -1188,17 +1178,17 @@ impl AiActionRepository for PostgresAiActionRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
-        .bind(&action.action_id)
-        .bind(&action.agent_id)
+        .bind(action.action_id)
+        .bind(action.agent_id)
         .bind(&action.name)
         .bind(&action.description)
         .bind(&action.input_schema)
         .bind(&action.output_schema)
         .bind(&action.handler_type)
         .bind(&action.handler_config)
-        .bind(&action.enabled)
-        .bind(&action.created_at)
-        .bind(&action.updated_at)
+        .bind(action.enabled)
+        .bind(action.created_at)
+        .bind(action.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -1216,7 +1206,7 @@ impl AiActionRepository for PostgresAiActionRepository {
             WHERE action_id = $1
             "#,
         )
-        .bind(&action_id)
+        .bind(action_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -1232,7 +1222,7 @@ impl AiActionRepository for PostgresAiActionRepository {
             WHERE agent_id = $1 AND LOWER(name) = LOWER($2)
             "#,
         )
-        .bind(&agent_id)
+        .bind(agent_id)
         .bind(name)
         .fetch_optional(&self.pool)
         .await?)
@@ -1250,7 +1240,7 @@ impl AiActionRepository for PostgresAiActionRepository {
             ORDER BY name
             "#,
         )
-        .bind(&agent_id)
+        .bind(agent_id)
         .fetch_all(&self.pool)
         .await?)
     }
@@ -1271,14 +1261,14 @@ impl AiActionRepository for PostgresAiActionRepository {
             WHERE action_id = $1
             "#,
         )
-        .bind(&action.action_id)
+        .bind(action.action_id)
         .bind(&action.name)
         .bind(&action.description)
         .bind(&action.input_schema)
         .bind(&action.output_schema)
         .bind(&action.handler_type)
         .bind(&action.handler_config)
-        .bind(&action.enabled)
+        .bind(action.enabled)
         .bind(Utc::now())
         .execute(&self.pool)
         .await?;
@@ -1293,7 +1283,7 @@ impl AiActionRepository for PostgresAiActionRepository {
             WHERE action_id = $1
             "#,
         )
-        .bind(&action_id)
+        .bind(action_id)
         .execute(&self.pool)
         .await?;
 
@@ -1322,13 +1312,13 @@ impl AiSystemPromptRepository for PostgresAiSystemPromptRepository {
             ) VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
-        .bind(&prompt.prompt_id)
+        .bind(prompt.prompt_id)
         .bind(&prompt.name)
         .bind(&prompt.content)
         .bind(&prompt.description)
-        .bind(&prompt.is_default)
-        .bind(&prompt.created_at)
-        .bind(&prompt.updated_at)
+        .bind(prompt.is_default)
+        .bind(prompt.created_at)
+        .bind(prompt.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -1345,7 +1335,7 @@ impl AiSystemPromptRepository for PostgresAiSystemPromptRepository {
             WHERE prompt_id = $1
             "#,
         )
-        .bind(&prompt_id)
+        .bind(prompt_id)
         .fetch_optional(&self.pool)
         .await?)
     }
@@ -1406,11 +1396,11 @@ impl AiSystemPromptRepository for PostgresAiSystemPromptRepository {
             WHERE prompt_id = $1
             "#,
         )
-        .bind(&prompt.prompt_id)
+        .bind(prompt.prompt_id)
         .bind(&prompt.name)
         .bind(&prompt.content)
         .bind(&prompt.description)
-        .bind(&prompt.is_default)
+        .bind(prompt.is_default)
         .bind(Utc::now())
         .execute(&self.pool)
         .await?;
@@ -1442,7 +1432,7 @@ impl AiSystemPromptRepository for PostgresAiSystemPromptRepository {
             WHERE prompt_id = $1
             "#,
         )
-        .bind(&prompt_id)
+        .bind(prompt_id)
         .bind(Utc::now())
         .execute(&mut *tx)
         .await?;
@@ -1460,7 +1450,7 @@ impl AiSystemPromptRepository for PostgresAiSystemPromptRepository {
             WHERE prompt_id = $1
             "#,
         )
-        .bind(&prompt_id)
+        .bind(prompt_id)
         .execute(&self.pool)
         .await?;
 
@@ -1505,7 +1495,7 @@ impl AiConfigurationRepository for PostgresAiConfigurationRepository {
                 WHERE provider_id = $1 AND is_default = true
                 "#,
             )
-            .bind(&provider.provider_id)
+            .bind(provider.provider_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -1525,7 +1515,7 @@ impl AiConfigurationRepository for PostgresAiConfigurationRepository {
                     WHERE provider_id = $1 AND is_default = true
                     "#,
                 )
-                .bind(&provider.provider_id)
+                .bind(provider.provider_id)
                 .fetch_optional(&self.pool)
                 .await?;
 
@@ -1565,7 +1555,7 @@ impl AiConfigurationRepository for PostgresAiConfigurationRepository {
                 WHERE provider_id = $1 AND is_default = true
                 "#,
             )
-            .bind(&provider.provider_id)
+            .bind(provider.provider_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -1585,7 +1575,7 @@ impl AiConfigurationRepository for PostgresAiConfigurationRepository {
                     WHERE provider_id = $1 AND is_default = true
                     "#,
                 )
-                .bind(&provider.provider_id)
+                .bind(provider.provider_id)
                 .fetch_optional(&self.pool)
                 .await?;
 
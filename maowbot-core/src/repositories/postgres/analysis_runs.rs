@@ -0,0 +1,114 @@
+// File: maowbot-core/src/repositories/postgres/analysis_runs.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::tasks::analysis_recompute::{AnalysisRunRepository, RecomputeRun};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct PostgresAnalysisRunRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresAnalysisRunRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AnalysisRunRepository for PostgresAnalysisRunRepository {
+    async fn mark_interrupted(&self) -> Result<u64, Error> {
+        let result = sqlx::query(
+            "UPDATE analysis_recompute_runs SET status = 'interrupted', updated_at = NOW() WHERE status = 'running'"
+        )
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn create_run(&self, since: DateTime<Utc>, until: DateTime<Utc>, total_users: i32) -> Result<RecomputeRun, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>(
+            r#"
+            INSERT INTO analysis_recompute_runs (run_id, since, until, status, total_users)
+            VALUES ($1, $2, $3, 'running', $4)
+            RETURNING *
+            "#,
+        )
+            .bind(Uuid::new_v4())
+            .bind(since)
+            .bind(until)
+            .bind(total_users)
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn resume_latest(&self) -> Result<Option<RecomputeRun>, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>(
+            r#"
+            UPDATE analysis_recompute_runs
+            SET status = 'running', error = NULL, updated_at = NOW()
+            WHERE run_id = (
+                SELECT run_id FROM analysis_recompute_runs
+                WHERE status IN ('cancelled', 'interrupted', 'failed')
+                ORDER BY started_at DESC
+                LIMIT 1
+            )
+            RETURNING *
+            "#,
+        )
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn latest(&self) -> Result<Option<RecomputeRun>, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>(
+            "SELECT * FROM analysis_recompute_runs ORDER BY started_at DESC LIMIT 1"
+        )
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn get(&self, run_id: Uuid) -> Result<Option<RecomputeRun>, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>("SELECT * FROM analysis_recompute_runs WHERE run_id = $1")
+            .bind(run_id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn save_checkpoint(&self, run_id: Uuid, processed_users: i32, last_user_id: Uuid) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE analysis_recompute_runs
+            SET processed_users = $2, last_user_id = $3, updated_at = NOW()
+            WHERE run_id = $1
+            "#,
+        )
+            .bind(run_id)
+            .bind(processed_users)
+            .bind(last_user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn finish(&self, run_id: Uuid, status: &str, error: Option<&str>) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE analysis_recompute_runs
+            SET status = $2, error = $3, updated_at = NOW(),
+                finished_at = CASE WHEN $2 = 'finished' THEN NOW() ELSE finished_at END
+            WHERE run_id = $1
+            "#,
+        )
+            .bind(run_id)
+            .bind(status)
+            .bind(error)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
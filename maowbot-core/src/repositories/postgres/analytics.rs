@@ -6,9 +6,12 @@ use sqlx::{Pool, Postgres, QueryBuilder};
 use uuid::Uuid;
 pub(crate) use maowbot_common::traits::repository_traits::AnalyticsRepo;
 pub(crate) use maowbot_common::models::analytics::{BotEvent, ChatMessage, ChatSession};
+use maowbot_common::models::analytics::count_activity;
 use crate::Error;
+use super::chat_archive::{bump_chat_activity, PostgresChatArchiveRepository};
 
-
+/// Rows per multi-row INSERT; 7 bind parameters each keeps well under Postgres' limit.
+const MAX_ROWS_PER_INSERT: usize = 1000;
 
 #[derive(Clone)]
 pub struct PostgresAnalyticsRepository {
@@ -19,6 +22,11 @@ impl PostgresAnalyticsRepository {
     pub fn new(pool: Pool<Postgres>) -> Self {
         Self { pool }
     }
+
+    /// Search and retention over the same `chat_messages` table.
+    pub fn chat_archive(&self) -> PostgresChatArchiveRepository {
+        PostgresChatArchiveRepository::new(self.pool.clone())
+    }
 }
 
 #[async_trait]
@@ -28,6 +36,7 @@ impl AnalyticsRepo for PostgresAnalyticsRepository {
     // Single insert
     // ----------------------------------------------------------------
     async fn insert_chat_message(&self, msg: &ChatMessage) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
         sqlx::query(
             r#"
             INSERT INTO chat_messages (
@@ -44,8 +53,10 @@ impl AnalyticsRepo for PostgresAnalyticsRepository {
             .bind(&msg.message_text)
             .bind(msg.timestamp)
             .bind(&msg.metadata)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+        bump_chat_activity(&mut *tx, &count_activity(std::slice::from_ref(msg))).await?;
+        tx.commit().await?;
 
         Ok(())
     }
@@ -58,29 +69,29 @@ impl AnalyticsRepo for PostgresAnalyticsRepository {
             return Ok(());
         }
 
-        // Construct the INSERT with columns:
-        let mut builder = QueryBuilder::new(
-            r#"INSERT INTO chat_messages (
-            message_id, platform, channel, user_id,
-            message_text, timestamp, metadata
-        ) "#
-        );
-
-        // Now we say `VALUES ` explicitly, then push each row via `push_values`:
-        // builder.push("VALUES ");
-        builder.push_values(msgs, |mut row, msg| {
-            row.push_bind(msg.message_id)
-                .push_bind(&msg.platform)
-                .push_bind(&msg.channel)
-                .push_bind(msg.user_id)
-                .push_bind(&msg.message_text)
-                .push_bind(msg.timestamp)
-                .push_bind(&msg.metadata);
-        });
-
-        // Build and execute
-        let query = builder.build();
-        query.execute(&self.pool).await?;
+        // One multi-row INSERT per chunk (Postgres allows 65535 bind parameters per
+        // statement), all in one transaction together with the chat_activity counts
+        let mut tx = self.pool.begin().await?;
+        for chunk in msgs.chunks(MAX_ROWS_PER_INSERT) {
+            let mut builder = QueryBuilder::new(
+                r#"INSERT INTO chat_messages (
+                message_id, platform, channel, user_id,
+                message_text, timestamp, metadata
+            ) "#
+            );
+            builder.push_values(chunk, |mut row, msg| {
+                row.push_bind(msg.message_id)
+                    .push_bind(&msg.platform)
+                    .push_bind(&msg.channel)
+                    .push_bind(msg.user_id)
+                    .push_bind(&msg.message_text)
+                    .push_bind(msg.timestamp)
+                    .push_bind(&msg.metadata);
+            });
+            builder.build().execute(&mut *tx).await?;
+        }
+        bump_chat_activity(&mut *tx, &count_activity(msgs)).await?;
+        tx.commit().await?;
 
         Ok(())
     }
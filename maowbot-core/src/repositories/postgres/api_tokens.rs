@@ -0,0 +1,183 @@
+// File: maowbot-core/src/repositories/postgres/api_tokens.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::api_token::{ApiToken, AuditLogEntry, AuditLogFilter};
+pub use maowbot_common::traits::repository_traits::ApiTokenRepository;
+
+#[derive(Clone)]
+pub struct PostgresApiTokenRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresApiTokenRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_token(r: &PgRow) -> Result<ApiToken, Error> {
+    let role: String = r.try_get("role")?;
+    Ok(ApiToken {
+        token_id: r.try_get("token_id")?,
+        name: r.try_get("name")?,
+        role: role.parse()?,
+        token_hash: r.try_get("token_hash")?,
+        created_at: r.try_get("created_at")?,
+        last_used_at: r.try_get("last_used_at")?,
+        revoked_at: r.try_get("revoked_at")?,
+        workspace_id: r.try_get("workspace_id")?,
+    })
+}
+
+fn row_to_audit_entry(r: &PgRow) -> Result<AuditLogEntry, Error> {
+    Ok(AuditLogEntry {
+        audit_id: r.try_get("audit_id")?,
+        token_id: r.try_get("token_id")?,
+        client_name: r.try_get("client_name")?,
+        role: r.try_get("role")?,
+        method: r.try_get("method")?,
+        workspace: r.try_get("workspace")?,
+        allowed: r.try_get("allowed")?,
+        reason: r.try_get("reason")?,
+        created_at: r.try_get("created_at")?,
+        target: r.try_get("target")?,
+        before_value: r.try_get("before_value")?,
+        after_value: r.try_get("after_value")?,
+        outcome: r.try_get("outcome")?,
+    })
+}
+
+#[async_trait]
+impl ApiTokenRepository for PostgresApiTokenRepository {
+    async fn create_token(&self, token: &ApiToken) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO api_tokens (token_id, name, role, token_hash, created_at, last_used_at, revoked_at, workspace_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+            .bind(token.token_id)
+            .bind(&token.name)
+            .bind(token.role.to_string())
+            .bind(&token.token_hash)
+            .bind(token.created_at)
+            .bind(token.last_used_at)
+            .bind(token.revoked_at)
+            .bind(token.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_token_by_name(&self, name: &str) -> Result<Option<ApiToken>, Error> {
+        let row_opt = sqlx::query(
+            r#"
+            SELECT token_id, name, role, token_hash, created_at, last_used_at, revoked_at, workspace_id
+            FROM api_tokens
+            WHERE name = $1
+            "#,
+        )
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_token).transpose()
+    }
+
+    async fn list_tokens(&self) -> Result<Vec<ApiToken>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT token_id, name, role, token_hash, created_at, last_used_at, revoked_at, workspace_id
+            FROM api_tokens
+            ORDER BY name ASC
+            "#,
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_token).collect()
+    }
+
+    async fn revoke_token(&self, token_id: Uuid) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE api_tokens SET revoked_at = NOW() WHERE token_id = $1 AND revoked_at IS NULL"
+        )
+            .bind(token_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn reset_token_hash(&self, token_id: Uuid, token_hash: &str) -> Result<(), Error> {
+        sqlx::query("UPDATE api_tokens SET token_hash = $2, revoked_at = NULL WHERE token_id = $1")
+            .bind(token_id)
+            .bind(token_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn touch_token(&self, token_id: Uuid, used_at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query("UPDATE api_tokens SET last_used_at = $2 WHERE token_id = $1")
+            .bind(token_id)
+            .bind(used_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn insert_audit_entry(&self, entry: &AuditLogEntry) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO grpc_audit_log (
+                token_id, client_name, role, method, workspace, allowed, reason, created_at,
+                target, before_value, after_value, outcome
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+        )
+            .bind(entry.token_id)
+            .bind(&entry.client_name)
+            .bind(&entry.role)
+            .bind(&entry.method)
+            .bind(&entry.workspace)
+            .bind(entry.allowed)
+            .bind(&entry.reason)
+            .bind(entry.created_at)
+            .bind(&entry.target)
+            .bind(&entry.before_value)
+            .bind(&entry.after_value)
+            .bind(&entry.outcome)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_audit_entries(&self, filter: &AuditLogFilter, limit: i64) -> Result<Vec<AuditLogEntry>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT audit_id, token_id, client_name, role, method, workspace, allowed, reason, created_at,
+                   target, before_value, after_value, outcome
+            FROM grpc_audit_log
+            WHERE ($1 IS NULL OR method ILIKE '%' || $1 || '%')
+              AND ($2 IS NULL OR client_name ILIKE '%' || $2 || '%')
+              AND ($3 IS NULL OR target ILIKE '%' || $3 || '%')
+              AND ($4 IS NULL OR created_at >= $4)
+              AND (NOT $5 OR outcome IS NOT NULL)
+            ORDER BY created_at DESC, audit_id DESC
+            LIMIT $6
+            "#,
+        )
+            .bind(&filter.method)
+            .bind(&filter.client_name)
+            .bind(&filter.target)
+            .bind(filter.since)
+            .bind(filter.changes_only)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_audit_entry).collect()
+    }
+}
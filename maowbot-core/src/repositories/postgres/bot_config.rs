@@ -2,16 +2,32 @@ use sqlx::{Pool, Postgres, Row};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 pub(crate) use maowbot_common::traits::repository_traits::BotConfigRepository;
+use uuid::Uuid;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use crate::crypto::Encryptor;
 use crate::Error;
 
+/// Bot config for one workspace (the default workspace unless `for_workspace` is used).
+/// Rows flagged `is_sensitive` hold secrets encrypted with the master key.
 #[derive(Clone)]
 pub struct PostgresBotConfigRepository {
     pool: Pool<Postgres>,
+    encryptor: Encryptor,
+    workspace_id: Uuid,
 }
 
 impl PostgresBotConfigRepository {
-    pub fn new(pool: Pool<Postgres>) -> Self {
-        Self { pool }
+    pub fn new(pool: Pool<Postgres>, encryptor: Encryptor) -> Self {
+        Self { pool, encryptor, workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+
+    /// A repository over the same pool that reads and writes `workspace_id`'s config.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { pool: self.pool.clone(), encryptor: self.encryptor.clone(), workspace_id }
+    }
+
+    pub fn workspace_id(&self) -> Uuid {
+        self.workspace_id
     }
 }
 
@@ -22,9 +38,11 @@ impl BotConfigRepository for PostgresBotConfigRepository {
             r#"
             SELECT config_value
             FROM bot_config
-            WHERE config_key = 'callback_port'
+            WHERE workspace_id = $1
+              AND config_key = 'callback_port'
             "#,
         )
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -48,22 +66,21 @@ impl BotConfigRepository for PostgresBotConfigRepository {
     // -----------------------------------------------------------------------
     // "set_value" for old usage
     //
-    // If you truly only want 1 row per `config_key`, you can choose a dummy
-    // `config_value` like empty string ("") or the same as `config_key`.
-    // But the ON CONFLICT must match (config_key, config_value).
+    // One row per (workspace_id, config_key); setting a key replaces its value.
     // -----------------------------------------------------------------------
     async fn set_value(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
-        // Use config_meta=NULL, composite key: (config_key, config_value)
         sqlx::query(
             r#"
-            INSERT INTO bot_config (config_key, config_value, config_meta)
-            VALUES ($1, $2, NULL)
-            ON CONFLICT (config_key, config_value)
+            INSERT INTO bot_config (workspace_id, config_key, config_value, config_meta)
+            VALUES ($1, $2, $3, NULL)
+            ON CONFLICT (workspace_id, config_key)
             DO UPDATE
                SET config_value = EXCLUDED.config_value,
-                   config_meta  = NULL
+                   config_meta  = NULL,
+                   is_sensitive = FALSE
             "#,
         )
+            .bind(self.workspace_id)
             .bind(config_key)
             .bind(config_value)
             .execute(&self.pool)
@@ -80,10 +97,13 @@ impl BotConfigRepository for PostgresBotConfigRepository {
             r#"
             SELECT config_value
             FROM bot_config
-            WHERE config_key = $1
+            WHERE workspace_id = $1
+              AND config_key = $2
+              AND is_sensitive IS NOT TRUE
             LIMIT 1
             "#,
         )
+            .bind(self.workspace_id)
             .bind(config_key)
             .fetch_optional(&self.pool)
             .await?;
@@ -97,7 +117,8 @@ impl BotConfigRepository for PostgresBotConfigRepository {
     }
 
     async fn list_all(&self) -> Result<Vec<(String, String)>, Error> {
-        let rows = sqlx::query("SELECT config_key, config_value FROM bot_config")
+        let rows = sqlx::query("SELECT config_key, config_value FROM bot_config WHERE workspace_id = $1 AND is_sensitive IS NOT TRUE")
+            .bind(self.workspace_id)
             .fetch_all(&self.pool)
             .await?;
 
@@ -117,9 +138,11 @@ impl BotConfigRepository for PostgresBotConfigRepository {
         sqlx::query(
             r#"
             DELETE FROM bot_config
-            WHERE config_key = $1
+            WHERE workspace_id = $1
+              AND config_key = $2
             "#,
         )
+            .bind(self.workspace_id)
             .bind(config_key)
             .execute(&self.pool)
             .await?;
@@ -129,7 +152,7 @@ impl BotConfigRepository for PostgresBotConfigRepository {
 
     // ------------------------------------------------------------------------
     // Extended usage: set_value_kv_meta and get_value_kv_meta
-    // (multiple rows with different config_value per config_key).
+    // (a value plus JSON metadata per config_key).
     // ------------------------------------------------------------------------
     async fn set_value_kv_meta(
         &self,
@@ -139,13 +162,16 @@ impl BotConfigRepository for PostgresBotConfigRepository {
     ) -> Result<(), Error> {
         sqlx::query(
             r#"
-            INSERT INTO bot_config (config_key, config_value, config_meta)
-            VALUES ($1, $2, $3::jsonb)
-            ON CONFLICT (config_key, config_value)
+            INSERT INTO bot_config (workspace_id, config_key, config_value, config_meta)
+            VALUES ($1, $2, $3, $4::jsonb)
+            ON CONFLICT (workspace_id, config_key)
             DO UPDATE
-               SET config_meta = EXCLUDED.config_meta
+               SET config_value = EXCLUDED.config_value,
+                   config_meta  = EXCLUDED.config_meta,
+                   is_sensitive = FALSE
             "#,
         )
+            .bind(self.workspace_id)
             .bind(config_key)
             .bind(config_value)
             .bind(config_meta)
@@ -164,10 +190,13 @@ impl BotConfigRepository for PostgresBotConfigRepository {
             r#"
             SELECT config_value, config_meta
             FROM bot_config
-            WHERE config_key = $1
-              AND config_value = $2
+            WHERE workspace_id = $1
+              AND config_key = $2
+              AND config_value = $3
+              AND is_sensitive IS NOT TRUE
             "#,
         )
+            .bind(self.workspace_id)
             .bind(config_key)
             .bind(config_value)
             .fetch_optional(&self.pool)
@@ -186,10 +215,12 @@ impl BotConfigRepository for PostgresBotConfigRepository {
         sqlx::query(
             r#"
             DELETE FROM bot_config
-            WHERE config_key = $1
-              AND config_value = $2
+            WHERE workspace_id = $1
+              AND config_key = $2
+              AND config_value = $3
             "#,
         )
+            .bind(self.workspace_id)
             .bind(config_key)
             .bind(config_value)
             .execute(&self.pool)
@@ -197,4 +228,58 @@ impl BotConfigRepository for PostgresBotConfigRepository {
 
         Ok(())
     }
+
+    // ------------------------------------------------------------------------
+    // Secrets: encrypted values flagged is_sensitive, re-encrypted on key rotation.
+    // ------------------------------------------------------------------------
+    async fn set_secret(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        let _write = self.encryptor.write_guard().await;
+        sqlx::query(
+            r#"
+            INSERT INTO bot_config (workspace_id, config_key, config_value, config_meta, is_sensitive)
+            VALUES ($1, $2, $3, NULL, TRUE)
+            ON CONFLICT (workspace_id, config_key)
+            DO UPDATE
+               SET config_value = EXCLUDED.config_value,
+                   config_meta  = NULL,
+                   is_sensitive = TRUE
+            "#,
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .bind(self.encryptor.encrypt(config_value)?)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_secret(&self, config_key: &str) -> Result<Option<String>, Error> {
+        let row_opt = sqlx::query(
+            r#"
+            SELECT config_value
+            FROM bot_config
+            WHERE workspace_id = $1
+              AND config_key = $2
+              AND is_sensitive
+            "#,
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row_opt {
+            Some(row) => Ok(Some(self.encryptor.decrypt(&row.try_get::<String, _>("config_value")?)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_secret_keys(&self) -> Result<Vec<String>, Error> {
+        let rows = sqlx::query("SELECT config_key FROM bot_config WHERE workspace_id = $1 AND is_sensitive")
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|r| r.try_get("config_key").map_err(Error::from)).collect()
+    }
 }
@@ -0,0 +1,271 @@
+// File: maowbot-core/src/repositories/postgres/chat_archive.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::ChatArchiveRepository;
+use maowbot_common::models::analytics::{ChatActivityBucket, ChatMessage, ChatRetentionPolicy, ChatSearchQuery};
+use crate::Error;
+
+const MESSAGE_COLUMNS: &str = "message_id, platform, channel, user_id, message_text, timestamp, metadata";
+
+/// Messages past their channel's retention, or `$1` days where the channel has
+/// none; prefixed with DELETE or SELECT COUNT(*). The outer cutoff (shortest
+/// retention in use) lets Postgres skip recent partitions.
+const EXPIRED_MESSAGES: &str = r#"
+    FROM chat_messages m
+    WHERE m.timestamp < NOW() - make_interval(days => (
+            SELECT LEAST($1::int, COALESCE(MIN(retention_days), $1::int)) FROM chat_logging_config))
+      AND m.timestamp < NOW() - make_interval(days => COALESCE(
+            (SELECT c.retention_days FROM chat_logging_config c
+             WHERE LOWER(c.platform) = LOWER(m.platform)
+               AND LOWER(LTRIM(c.channel, '#')) = LOWER(LTRIM(m.channel, '#'))),
+            $1::int))
+"#;
+
+#[derive(Clone)]
+pub struct PostgresChatArchiveRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresChatArchiveRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Appends the WHERE clause for `query` (without ORDER/LIMIT).
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ChatSearchQuery) {
+    builder.push(" WHERE TRUE");
+    if let Some(user_id) = query.user_id {
+        builder.push(" AND user_id = ").push_bind(user_id);
+    }
+    if let Some(platform) = &query.platform {
+        builder.push(" AND LOWER(platform) = LOWER(").push_bind(platform.clone()).push(")");
+    }
+    if let Some(channel) = &query.channel {
+        // Twitch channels are stored as "#name", Discord ones without; accept either form
+        builder.push(" AND LOWER(LTRIM(channel, '#')) = LOWER(").push_bind(channel.trim_start_matches('#').to_string()).push(")");
+    }
+    if let Some(text) = query.text.as_deref().filter(|t| !t.trim().is_empty()) {
+        builder.push(" AND search_vector @@ websearch_to_tsquery('simple', ").push_bind(text.to_string()).push(")");
+    }
+    if let Some(since) = query.since {
+        builder.push(" AND timestamp >= ").push_bind(since);
+    }
+    if let Some(until) = query.until {
+        builder.push(" AND timestamp < ").push_bind(until);
+    }
+}
+
+#[async_trait]
+impl ChatArchiveRepository for PostgresChatArchiveRepository {
+    async fn search_messages(&self, query: &ChatSearchQuery) -> Result<(Vec<ChatMessage>, i64), Error> {
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM chat_messages");
+        push_filters(&mut count, query);
+        let total: i64 = count.build().fetch_one(&self.pool).await?.try_get(0)?;
+
+        let mut select = QueryBuilder::new(format!("SELECT {} FROM chat_messages", MESSAGE_COLUMNS));
+        push_filters(&mut select, query);
+        select.push(" ORDER BY timestamp DESC LIMIT ").push_bind(query.limit.max(1))
+            .push(" OFFSET ").push_bind(query.offset.max(0));
+        let messages = select.build_query_as::<ChatMessage>().fetch_all(&self.pool).await?;
+
+        Ok((messages, total))
+    }
+
+    async fn last_message_for_user(
+        &self,
+        user_id: Uuid,
+        maybe_platform: Option<&str>,
+        maybe_channel: Option<&str>,
+    ) -> Result<Option<ChatMessage>, Error> {
+        let query = ChatSearchQuery {
+            user_id: Some(user_id),
+            platform: maybe_platform.map(str::to_string),
+            channel: maybe_channel.map(str::to_string),
+            limit: 1,
+            ..Default::default()
+        };
+        let mut select = QueryBuilder::new(format!("SELECT {} FROM chat_messages", MESSAGE_COLUMNS));
+        push_filters(&mut select, &query);
+        select.push(" ORDER BY timestamp DESC LIMIT 1");
+        Ok(select.build_query_as::<ChatMessage>().fetch_optional(&self.pool).await?)
+    }
+
+    async fn list_retention_policies(&self) -> Result<Vec<ChatRetentionPolicy>, Error> {
+        let rows = sqlx::query_as::<_, ChatRetentionPolicy>(
+            r#"
+            SELECT platform, channel, retention_days, is_enabled, updated_at
+            FROM chat_logging_config
+            ORDER BY platform, channel
+            "#
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    async fn set_retention_policy(&self, platform: &str, channel: &str, retention_days: i32) -> Result<(), Error> {
+        if retention_days <= 0 {
+            return Err(Error::ValidationError("Retention must be at least 1 day".into()));
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO chat_logging_config (platform, channel, retention_days)
+            VALUES (LOWER($1), LOWER($2), $3)
+            ON CONFLICT (platform, channel) DO UPDATE
+              SET retention_days = EXCLUDED.retention_days,
+                  updated_at = NOW()
+            "#
+        )
+            .bind(platform)
+            .bind(channel.trim_start_matches('#'))
+            .bind(retention_days)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_retention_policy(&self, platform: &str, channel: &str) -> Result<bool, Error> {
+        let res = sqlx::query(
+            "DELETE FROM chat_logging_config WHERE LOWER(platform) = LOWER($1) AND LOWER(LTRIM(channel, '#')) = LOWER($2)"
+        )
+            .bind(platform)
+            .bind(channel.trim_start_matches('#'))
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn purge_expired_messages(&self, default_days: i64) -> Result<u64, Error> {
+        let res = sqlx::query(&format!("DELETE {}", EXPIRED_MESSAGES))
+            .bind(default_days as i32)
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected())
+    }
+
+    async fn count_expired_messages(&self, default_days: i64) -> Result<u64, Error> {
+        let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", EXPIRED_MESSAGES))
+            .bind(default_days as i32)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count as u64)
+    }
+
+    async fn chat_activity(
+        &self,
+        maybe_platform: Option<&str>,
+        maybe_channel: Option<&str>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ChatActivityBucket>, Error> {
+        let rows = sqlx::query_as::<_, ChatActivityBucket>(
+            r#"
+            SELECT platform, channel, bucket_start, message_count
+            FROM chat_activity
+            WHERE bucket_start >= $1
+              AND bucket_start < $2
+              AND ($3::text IS NULL OR LOWER(platform) = LOWER($3))
+              AND ($4::text IS NULL OR LOWER(LTRIM(channel, '#')) = LOWER($4))
+            ORDER BY platform, channel, bucket_start
+            "#
+        )
+            .bind(since)
+            .bind(until)
+            .bind(maybe_platform)
+            .bind(maybe_channel.map(|c| c.trim_start_matches('#')))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    async fn count_chatters(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(DISTINCT user_id) FROM chat_messages WHERE timestamp >= $1 AND timestamp < $2"
+        )
+            .bind(since)
+            .bind(until)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    async fn chatters(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, Error> {
+        let users: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT user_id FROM chat_messages
+            WHERE timestamp >= $1 AND timestamp < $2
+              AND ($3::uuid IS NULL OR user_id > $3)
+            ORDER BY user_id
+            LIMIT $4
+            "#,
+        )
+            .bind(since)
+            .bind(until)
+            .bind(after)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(users)
+    }
+
+    async fn messages_for_user_between(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: Option<i64>,
+    ) -> Result<Vec<ChatMessage>, Error> {
+        // LIMIT NULL is no limit
+        let messages = sqlx::query_as::<_, ChatMessage>(&format!(
+            r#"
+            SELECT {} FROM chat_messages
+            WHERE user_id = $1 AND timestamp >= $2 AND timestamp < $3
+            ORDER BY timestamp DESC
+            LIMIT $4
+            "#,
+            MESSAGE_COLUMNS
+        ))
+            .bind(user_id)
+            .bind(since)
+            .bind(until)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(messages)
+    }
+}
+
+/// Adds `buckets` to the stored `chat_activity` counts.
+pub(crate) async fn bump_chat_activity<'e, E>(executor: E, buckets: &[ChatActivityBucket]) -> Result<(), Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    if buckets.is_empty() {
+        return Ok(());
+    }
+    let mut builder = QueryBuilder::new(
+        "INSERT INTO chat_activity (platform, channel, bucket_start, message_count) "
+    );
+    builder.push_values(buckets, |mut row, b| {
+        row.push_bind(&b.platform)
+            .push_bind(&b.channel)
+            .push_bind(b.bucket_start)
+            .push_bind(b.message_count);
+    });
+    builder.push(
+        " ON CONFLICT (platform, channel, bucket_start) \
+          DO UPDATE SET message_count = chat_activity.message_count + EXCLUDED.message_count"
+    );
+    builder.build().execute(executor).await?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+// File: maowbot-core/src/repositories/postgres/clips.rs
+
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+pub use maowbot_common::traits::repository_traits::ClipRepository;
+use maowbot_common::models::clip::PostedClip;
+use crate::Error;
+
+#[derive(Clone)]
+pub struct PostgresClipRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresClipRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn clip_from_row(row: &PgRow) -> Result<PostedClip, Error> {
+    Ok(PostedClip {
+        clip_id: row.try_get("clip_id")?,
+        broadcaster_id: row.try_get("broadcaster_id")?,
+        title: row.try_get("title")?,
+        creator_name: row.try_get("creator_name")?,
+        url: row.try_get("url")?,
+        created_at: row.try_get("created_at")?,
+        posted_at: row.try_get("posted_at")?,
+        requested_by: row.try_get("requested_by")?,
+    })
+}
+
+#[async_trait]
+impl ClipRepository for PostgresClipRepository {
+    async fn insert_clip(&self, clip: &PostedClip) -> Result<bool, Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO posted_clips (
+                clip_id, broadcaster_id, title, creator_name,
+                url, created_at, posted_at, requested_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (clip_id) DO NOTHING
+            "#
+        )
+            .bind(&clip.clip_id)
+            .bind(&clip.broadcaster_id)
+            .bind(&clip.title)
+            .bind(&clip.creator_name)
+            .bind(&clip.url)
+            .bind(clip.created_at)
+            .bind(clip.posted_at)
+            .bind(&clip.requested_by)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn posted_clip_ids(&self, clip_ids: &[String]) -> Result<Vec<String>, Error> {
+        if clip_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = sqlx::query_scalar("SELECT clip_id FROM posted_clips WHERE clip_id = ANY($1)")
+            .bind(clip_ids)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(ids)
+    }
+
+    async fn list_clips(&self, limit: i64) -> Result<Vec<PostedClip>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT clip_id, broadcaster_id, title, creator_name,
+                   url, created_at, posted_at, requested_by
+            FROM posted_clips
+            ORDER BY posted_at DESC
+            LIMIT $1
+            "#
+        )
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(clip_from_row).collect()
+    }
+}
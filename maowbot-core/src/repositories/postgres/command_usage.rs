@@ -1,7 +1,9 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
 use sqlx::{Pool, Postgres, Row};
 use uuid::Uuid;
-use maowbot_common::models::CommandUsage;
+use maowbot_common::models::{CommandStats, CommandUsage};
 pub(crate) use maowbot_common::traits::repository_traits::CommandUsageRepository;
 use crate::Error;
 
@@ -20,25 +22,47 @@ impl PostgresCommandUsageRepository {
     }
 }
 
+const USAGE_COLUMNS: &str = "usage_id, command_id, user_id, platform, executed_at, channel, \
+    input_text, metadata, duration_ms, error_message";
+
+fn usage_from_row(row: &PgRow) -> Result<CommandUsage, Error> {
+    Ok(CommandUsage {
+        usage_id: row.try_get("usage_id")?,
+        command_id: row.try_get("command_id")?,
+        // The user may have been deleted since
+        user_id: row.try_get::<Option<Uuid>, _>("user_id")?.unwrap_or_default(),
+        platform: row.try_get("platform")?,
+        used_at: row.try_get("executed_at")?,
+        channel: row.try_get::<Option<String>, _>("channel")?.unwrap_or_default(),
+        usage_text: row.try_get::<Option<String>, _>("input_text")?.unwrap_or_default(),
+        metadata: row.try_get("metadata")?,
+        duration_ms: row.try_get("duration_ms")?,
+        error_message: row.try_get("error_message")?,
+    })
+}
+
 #[async_trait]
 impl CommandUsageRepository for PostgresCommandUsageRepository {
     async fn insert_usage(&self, usage: &CommandUsage) -> Result<(), Error> {
         sqlx::query(
             r#"
             INSERT INTO command_usage (
-                usage_id, command_id, user_id, used_at,
-                channel, usage_text, metadata
+                usage_id, command_id, user_id, platform, executed_at,
+                channel, input_text, metadata, duration_ms, error_message
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
         )
             .bind(usage.usage_id)
             .bind(usage.command_id)
             .bind(usage.user_id)
+            .bind(&usage.platform)
             .bind(usage.used_at)
             .bind(&usage.channel)
             .bind(&usage.usage_text)
             .bind(&usage.metadata)
+            .bind(usage.duration_ms)
+            .bind(&usage.error_message)
             .execute(&self.pool)
             .await?;
 
@@ -46,66 +70,77 @@ impl CommandUsageRepository for PostgresCommandUsageRepository {
     }
 
     async fn list_usage_for_command(&self, command_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT usage_id, command_id, user_id, used_at,
-                   channel, usage_text, metadata
-            FROM command_usage
-            WHERE command_id = $1
-            ORDER BY used_at DESC
-            LIMIT $2
-            "#,
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {USAGE_COLUMNS} FROM command_usage WHERE command_id = $1 ORDER BY executed_at DESC LIMIT $2"
+        ))
             .bind(command_id)
             .bind(limit)
             .fetch_all(&self.pool)
             .await?;
 
-        let mut results = Vec::new();
-        for row in rows {
-            let cu = CommandUsage {
-                usage_id: row.try_get("usage_id")?,
-                command_id: row.try_get("command_id")?,
-                user_id: row.try_get("user_id")?,
-                used_at: row.try_get("used_at")?,
-                channel: row.try_get("channel")?,
-                usage_text: row.try_get("usage_text")?,
-                metadata: row.try_get("metadata")?,
-            };
-            results.push(cu);
-        }
-        Ok(results)
+        rows.iter().map(usage_from_row).collect()
     }
 
     async fn list_usage_for_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
-        let rows = sqlx::query(
+        let rows = sqlx::query(&format!(
+            "SELECT {USAGE_COLUMNS} FROM command_usage WHERE user_id = $1 ORDER BY executed_at DESC LIMIT $2"
+        ))
+            .bind(user_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(usage_from_row).collect()
+    }
+
+    async fn command_stats(&self, command_id: Uuid, since: DateTime<Utc>) -> Result<CommandStats, Error> {
+        let row = sqlx::query(
             r#"
-            SELECT usage_id, command_id, user_id, used_at,
-                   channel, usage_text, metadata
+            SELECT COUNT(*) AS uses,
+                   COUNT(*) FILTER (WHERE error_message IS NOT NULL) AS errors,
+                   COUNT(duration_ms) AS timed_uses,
+                   AVG(duration_ms)::float8 AS avg_ms,
+                   percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms) AS p50_ms,
+                   percentile_cont(0.95) WITHIN GROUP (ORDER BY duration_ms) AS p95_ms,
+                   percentile_cont(0.99) WITHIN GROUP (ORDER BY duration_ms) AS p99_ms,
+                   MAX(duration_ms)::float8 AS max_ms,
+                   MAX(executed_at) AS last_used_at
             FROM command_usage
-            WHERE user_id = $1
-            ORDER BY used_at DESC
-            LIMIT $2
+            WHERE command_id = $1 AND executed_at >= $2
             "#,
         )
-            .bind(user_id)
-            .bind(limit)
-            .fetch_all(&self.pool)
+            .bind(command_id)
+            .bind(since)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let last_error: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT error_message FROM command_usage
+            WHERE command_id = $1 AND executed_at >= $2 AND error_message IS NOT NULL
+            ORDER BY executed_at DESC
+            LIMIT 1
+            "#,
+        )
+            .bind(command_id)
+            .bind(since)
+            .fetch_optional(&self.pool)
             .await?;
 
-        let mut results = Vec::new();
-        for row in rows {
-            let cu = CommandUsage {
-                usage_id: row.try_get("usage_id")?,
-                command_id: row.try_get("command_id")?,
-                user_id: row.try_get("user_id")?,
-                used_at: row.try_get("used_at")?,
-                channel: row.try_get("channel")?,
-                usage_text: row.try_get("usage_text")?,
-                metadata: row.try_get("metadata")?,
-            };
-            results.push(cu);
-        }
-        Ok(results)
+        let ms = |column: &str| -> Result<f64, Error> {
+            Ok(row.try_get::<Option<f64>, _>(column)?.unwrap_or(0.0))
+        };
+        Ok(CommandStats {
+            uses: row.try_get("uses")?,
+            errors: row.try_get("errors")?,
+            timed_uses: row.try_get("timed_uses")?,
+            avg_ms: ms("avg_ms")?,
+            p50_ms: ms("p50_ms")?,
+            p95_ms: ms("p95_ms")?,
+            p99_ms: ms("p99_ms")?,
+            max_ms: ms("max_ms")?,
+            last_used_at: row.try_get("last_used_at")?,
+            last_error,
+        })
     }
 }
@@ -1,22 +1,28 @@
 // File: maowbot-core/src/repositories/postgres/commands.rs
 
-use std::str::FromStr;
+use std::collections::HashMap;
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres, Row};
+use sqlx::{types::Json, Pool, Postgres, Row};
 use uuid::Uuid;
-use chrono::Utc;
 use maowbot_common::error::Error;
-use maowbot_common::models::command::{Command, CommandUsage};
-use maowbot_common::traits::repository_traits::{CommandRepository, CommandUsageRepository};
-use maowbot_common::models::platform::Platform;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::models::command::Command;
+use maowbot_common::traits::repository_traits::CommandRepository;
 
+/// Commands for one workspace (the default workspace unless `for_workspace` is used).
 pub struct PostgresCommandRepository {
     pub pool: Pool<Postgres>,
+    pub workspace_id: Uuid,
 }
 
 impl PostgresCommandRepository {
     pub fn new(pool: Pool<Postgres>) -> Self {
-        Self { pool }
+        Self { pool, workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+
+    /// A repository over the same pool scoped to `workspace_id`.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { pool: self.pool.clone(), workspace_id }
     }
 }
 
@@ -38,9 +44,16 @@ impl CommandRepository for PostgresCommandRepository {
                 respond_with_credential,
                 stream_online_only,
                 stream_offline_only,
-                active_credential_id
+                active_credential_id,
+                workspace_id,
+                user_cooldown_seconds,
+                role_cooldowns,
+                cooldown_bypass_mods,
+                reply_privately,
+                reply_to_message,
+                response
             )
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13)
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18,$19,$20)
             "#,
         )
             .bind(cmd.command_id)
@@ -56,6 +69,13 @@ impl CommandRepository for PostgresCommandRepository {
             .bind(cmd.stream_online_only)
             .bind(cmd.stream_offline_only)
             .bind(cmd.active_credential_id)
+            .bind(self.workspace_id)
+            .bind(cmd.user_cooldown_seconds)
+            .bind(Json(&cmd.role_cooldowns))
+            .bind(cmd.cooldown_bypass_mods)
+            .bind(cmd.reply_privately)
+            .bind(cmd.reply_to_message)
+            .bind(&cmd.response)
             .execute(&self.pool)
             .await?;
 
@@ -78,12 +98,20 @@ impl CommandRepository for PostgresCommandRepository {
                 respond_with_credential,
                 stream_online_only,
                 stream_offline_only,
-                active_credential_id
+                active_credential_id,
+                user_cooldown_seconds,
+                role_cooldowns,
+                cooldown_bypass_mods,
+                reply_privately,
+                reply_to_message,
+                response
             FROM commands
             WHERE command_id = $1
+              AND workspace_id = $2
             "#,
         )
             .bind(command_id)
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -102,6 +130,12 @@ impl CommandRepository for PostgresCommandRepository {
                 stream_online_only: r.try_get("stream_online_only")?,
                 stream_offline_only: r.try_get("stream_offline_only")?,
                 active_credential_id: r.try_get("active_credential_id")?,
+                user_cooldown_seconds: r.try_get("user_cooldown_seconds")?,
+                role_cooldowns: r.try_get::<Json<HashMap<String, i32>>, _>("role_cooldowns")?.0,
+                cooldown_bypass_mods: r.try_get("cooldown_bypass_mods")?,
+                reply_privately: r.try_get("reply_privately")?,
+                reply_to_message: r.try_get("reply_to_message")?,
+                response: r.try_get("response")?,
             };
             Ok(Some(cmd))
         } else {
@@ -125,14 +159,22 @@ impl CommandRepository for PostgresCommandRepository {
                 respond_with_credential,
                 stream_online_only,
                 stream_offline_only,
-                active_credential_id
+                active_credential_id,
+                user_cooldown_seconds,
+                role_cooldowns,
+                cooldown_bypass_mods,
+                reply_privately,
+                reply_to_message,
+                response
             FROM commands
             WHERE LOWER(platform) = LOWER($1)
               AND LOWER(command_name) = LOWER($2)
+              AND workspace_id = $3
             "#,
         )
             .bind(platform)
             .bind(command_name)
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -151,6 +193,12 @@ impl CommandRepository for PostgresCommandRepository {
                 stream_online_only: r.try_get("stream_online_only")?,
                 stream_offline_only: r.try_get("stream_offline_only")?,
                 active_credential_id: r.try_get("active_credential_id")?,
+                user_cooldown_seconds: r.try_get("user_cooldown_seconds")?,
+                role_cooldowns: r.try_get::<Json<HashMap<String, i32>>, _>("role_cooldowns")?.0,
+                cooldown_bypass_mods: r.try_get("cooldown_bypass_mods")?,
+                reply_privately: r.try_get("reply_privately")?,
+                reply_to_message: r.try_get("reply_to_message")?,
+                response: r.try_get("response")?,
             };
             Ok(Some(cmd))
         } else {
@@ -174,13 +222,21 @@ impl CommandRepository for PostgresCommandRepository {
                 respond_with_credential,
                 stream_online_only,
                 stream_offline_only,
-                active_credential_id
+                active_credential_id,
+                user_cooldown_seconds,
+                role_cooldowns,
+                cooldown_bypass_mods,
+                reply_privately,
+                reply_to_message,
+                response
             FROM commands
             WHERE LOWER(platform) = LOWER($1)
+              AND workspace_id = $2
             ORDER BY command_name ASC
             "#,
         )
             .bind(platform)
+            .bind(self.workspace_id)
             .fetch_all(&self.pool)
             .await?;
 
@@ -200,6 +256,12 @@ impl CommandRepository for PostgresCommandRepository {
                 stream_online_only: r.try_get("stream_online_only")?,
                 stream_offline_only: r.try_get("stream_offline_only")?,
                 active_credential_id: r.try_get("active_credential_id")?,
+                user_cooldown_seconds: r.try_get("user_cooldown_seconds")?,
+                role_cooldowns: r.try_get::<Json<HashMap<String, i32>>, _>("role_cooldowns")?.0,
+                cooldown_bypass_mods: r.try_get("cooldown_bypass_mods")?,
+                reply_privately: r.try_get("reply_privately")?,
+                reply_to_message: r.try_get("reply_to_message")?,
+                response: r.try_get("response")?,
             };
             cmds.push(c);
         }
@@ -221,8 +283,15 @@ impl CommandRepository for PostgresCommandRepository {
                 respond_with_credential = $8,
                 stream_online_only = $9,
                 stream_offline_only = $10,
-                active_credential_id = $11
-            WHERE command_id = $12
+                active_credential_id = $11,
+                user_cooldown_seconds = $12,
+                role_cooldowns = $13,
+                cooldown_bypass_mods = $14,
+                reply_privately = $15,
+                reply_to_message = $16,
+                response = $17
+            WHERE command_id = $18
+              AND workspace_id = $19
             "#,
         )
             .bind(&cmd.platform)
@@ -236,110 +305,25 @@ impl CommandRepository for PostgresCommandRepository {
             .bind(cmd.stream_online_only)
             .bind(cmd.stream_offline_only)
             .bind(cmd.active_credential_id)
+            .bind(cmd.user_cooldown_seconds)
+            .bind(Json(&cmd.role_cooldowns))
+            .bind(cmd.cooldown_bypass_mods)
+            .bind(cmd.reply_privately)
+            .bind(cmd.reply_to_message)
+            .bind(&cmd.response)
             .bind(cmd.command_id)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 
     async fn delete_command(&self, command_id: Uuid) -> Result<(), Error> {
-        sqlx::query("DELETE FROM commands WHERE command_id = $1")
+        sqlx::query("DELETE FROM commands WHERE command_id = $1 AND workspace_id = $2")
             .bind(command_id)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 }
-
-pub struct PostgresCommandUsageRepository {
-    pub pool: Pool<Postgres>,
-}
-
-#[async_trait]
-impl CommandUsageRepository for PostgresCommandUsageRepository {
-    async fn insert_usage(&self, usage: &CommandUsage) -> Result<(), Error> {
-        sqlx::query(
-            r#"
-            INSERT INTO command_usage (
-                usage_id,
-                command_id,
-                user_id,
-                used_at,
-                channel,
-                usage_text,
-                metadata
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7)
-            "#,
-        )
-            .bind(usage.usage_id)
-            .bind(usage.command_id)
-            .bind(usage.user_id)
-            .bind(usage.used_at)
-            .bind(&usage.channel)
-            .bind(&usage.usage_text)
-            .bind(&usage.metadata)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
-
-    async fn list_usage_for_command(&self, command_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT usage_id, command_id, user_id, used_at, channel, usage_text, metadata
-            FROM command_usage
-            WHERE command_id = $1
-            ORDER BY used_at DESC
-            LIMIT $2
-            "#,
-        )
-            .bind(command_id)
-            .bind(limit)
-            .fetch_all(&self.pool)
-            .await?;
-
-        let mut out = Vec::new();
-        for r in rows {
-            out.push(CommandUsage {
-                usage_id: r.try_get("usage_id")?,
-                command_id: r.try_get("command_id")?,
-                user_id: r.try_get("user_id")?,
-                used_at: r.try_get("used_at")?,
-                channel: r.try_get("channel")?,
-                usage_text: r.try_get("usage_text")?,
-                metadata: r.try_get("metadata")?,
-            });
-        }
-        Ok(out)
-    }
-
-    async fn list_usage_for_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT usage_id, command_id, user_id, used_at, channel, usage_text, metadata
-            FROM command_usage
-            WHERE user_id = $1
-            ORDER BY used_at DESC
-            LIMIT $2
-            "#,
-        )
-            .bind(user_id)
-            .bind(limit)
-            .fetch_all(&self.pool)
-            .await?;
-
-        let mut out = Vec::new();
-        for r in rows {
-            out.push(CommandUsage {
-                usage_id: r.try_get("usage_id")?,
-                command_id: r.try_get("command_id")?,
-                user_id: r.try_get("user_id")?,
-                used_at: r.try_get("used_at")?,
-                channel: r.try_get("channel")?,
-                usage_text: r.try_get("usage_text")?,
-                metadata: r.try_get("metadata")?,
-            });
-        }
-        Ok(out)
-    }
-}
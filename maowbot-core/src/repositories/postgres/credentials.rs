@@ -7,17 +7,28 @@ use sqlx::{Pool, Postgres, Row};
 use std::str::FromStr;
 use uuid::Uuid;
 use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
 use maowbot_common::traits::repository_traits::CredentialsRepository;
 
+/// Platform credentials. Unlike the other workspace-scoped repositories this one
+/// sees every workspace by default, since platform runtimes, token refresh and key
+/// rotation are server-wide; `for_workspace` narrows it for per-broadcaster views.
 #[derive(Clone)]
 pub struct PostgresCredentialsRepository {
     pub pool: Pool<Postgres>,
     pub encryptor: Encryptor,
+    /// `None` = all workspaces (new credentials go to the default workspace).
+    pub workspace_id: Option<Uuid>,
 }
 
 impl PostgresCredentialsRepository {
     pub fn new(pool: Pool<Postgres>, encryptor: Encryptor) -> Self {
-        Self { pool, encryptor }
+        Self { pool, encryptor, workspace_id: None }
+    }
+
+    /// A repository over the same pool that only sees `workspace_id`'s credentials.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { workspace_id: Some(workspace_id), ..self.clone() }
     }
 }
 
@@ -27,7 +38,8 @@ impl CredentialsRepository for PostgresCredentialsRepository {
         let platform_str = creds.platform.to_string();
         let cred_type_str = creds.credential_type.to_string();
 
-        // Encrypt sensitive fields
+        // Encrypt sensitive fields; a key rotation waits until they're written
+        let _write = self.encryptor.write_guard().await;
         let encrypted_token = self.encryptor.encrypt(&creds.primary_token)?;
         let encrypted_refresh = match &creds.refresh_token {
             Some(token) => Some(self.encryptor.encrypt(token)?),
@@ -55,11 +67,12 @@ impl CredentialsRepository for PostgresCredentialsRepository {
                 updated_at,
                 is_bot,
                 is_teammate,
-                is_broadcaster
+                is_broadcaster,
+                workspace_id
             )
             VALUES ($1, $2, $3, $4, $5, $6,
                     $7, $8, $9, $10, $11, $12,
-                    $13, $14, $15)
+                    $13, $14, $15, $16)
             ON CONFLICT (platform, user_id) DO UPDATE
                SET
                  platform_id       = EXCLUDED.platform_id,
@@ -89,6 +102,7 @@ impl CredentialsRepository for PostgresCredentialsRepository {
             .bind(creds.is_bot)
             .bind(creds.is_teammate)
             .bind(creds.is_broadcaster)
+            .bind(self.workspace_id.unwrap_or(DEFAULT_WORKSPACE_ID))
             .execute(&self.pool)
             .await?;
 
@@ -117,10 +131,12 @@ impl CredentialsRepository for PostgresCredentialsRepository {
             FROM platform_credentials
             WHERE LOWER(platform) = LOWER($1)
               AND user_id = $2
+              AND ($3::uuid IS NULL OR workspace_id = $3)
             "#,
         )
             .bind(platform.to_string())
             .bind(user_id)
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -185,10 +201,12 @@ impl CredentialsRepository for PostgresCredentialsRepository {
                 is_broadcaster
             FROM platform_credentials
             WHERE credential_id = $1
+              AND ($2::uuid IS NULL OR workspace_id = $2)
             LIMIT 1
             "#,
         )
             .bind(credential_id)
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -235,6 +253,7 @@ impl CredentialsRepository for PostgresCredentialsRepository {
     async fn update_credentials(&self, creds: &PlatformCredential) -> Result<(), Error> {
         let platform_str = creds.platform.to_string();
 
+        let _write = self.encryptor.write_guard().await;
         let encrypted_token = self.encryptor.encrypt(&creds.primary_token)?;
         let encrypted_refresh = match &creds.refresh_token {
             Some(r) => Some(self.encryptor.encrypt(r)?),
@@ -261,6 +280,7 @@ impl CredentialsRepository for PostgresCredentialsRepository {
               is_broadcaster  = $10
             WHERE LOWER(platform) = LOWER($11)
               AND user_id = $12
+              AND ($13::uuid IS NULL OR workspace_id = $13)
             "#,
         )
             .bind(&creds.platform_id)
@@ -275,6 +295,7 @@ impl CredentialsRepository for PostgresCredentialsRepository {
             .bind(creds.is_broadcaster)
             .bind(platform_str)
             .bind(creds.user_id)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
 
@@ -287,10 +308,12 @@ impl CredentialsRepository for PostgresCredentialsRepository {
             DELETE FROM platform_credentials
             WHERE LOWER(platform) = LOWER($1)
               AND user_id = $2
+              AND ($3::uuid IS NULL OR workspace_id = $3)
             "#
         )
             .bind(platform.to_string())
             .bind(user_id)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
         Ok(())
@@ -319,9 +342,11 @@ impl CredentialsRepository for PostgresCredentialsRepository {
             FROM platform_credentials
             WHERE expires_at IS NOT NULL
               AND expires_at <= $1
+              AND ($2::uuid IS NULL OR workspace_id = $2)
             "#,
         )
             .bind(cutoff)
+            .bind(self.workspace_id)
             .fetch_all(&self.pool)
             .await?;
 
@@ -383,8 +408,10 @@ impl CredentialsRepository for PostgresCredentialsRepository {
                 is_teammate,
                 is_broadcaster
             FROM platform_credentials
+            WHERE ($1::uuid IS NULL OR workspace_id = $1)
             "#
         )
+            .bind(self.workspace_id)
             .fetch_all(&self.pool)
             .await?;
 
@@ -447,9 +474,11 @@ impl CredentialsRepository for PostgresCredentialsRepository {
                 is_broadcaster
             FROM platform_credentials
             WHERE LOWER(platform) = LOWER($1)
+              AND ($2::uuid IS NULL OR workspace_id = $2)
             "#,
         )
             .bind(platform.to_string())
+            .bind(self.workspace_id)
             .fetch_all(&self.pool)
             .await?;
 
@@ -514,4 +543,13 @@ impl CredentialsRepository for PostgresCredentialsRepository {
         let mates = all.into_iter().filter(|c| c.is_teammate).collect();
         Ok(mates)
     }
+
+    async fn assign_workspace(&self, credential_id: Uuid, workspace_id: Uuid) -> Result<(), Error> {
+        sqlx::query("UPDATE platform_credentials SET workspace_id = $1 WHERE credential_id = $2")
+            .bind(workspace_id)
+            .bind(credential_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
 }
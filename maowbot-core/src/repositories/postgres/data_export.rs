@@ -0,0 +1,159 @@
+// File: maowbot-core/src/repositories/postgres/data_export.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::postgres::PgRow;
+use sqlx::{Pool, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+
+use crate::services::data_export::{ExportKind, ExportQuery, ExportRepository, ExportRow};
+use crate::Error;
+
+/// Where a kind's rows come from, and the expressions its rows are filtered
+/// and paged by.
+struct Source {
+    select: &'static str,
+    at: &'static str,
+    id: &'static str,
+    platform: &'static str,
+    channel: &'static str,
+}
+
+fn export_source(kind: ExportKind) -> Source {
+    match kind {
+        ExportKind::Chat => Source {
+            select: r#"
+                SELECT m.message_id AS id, m.timestamp AS at, m.platform, m.channel, m.user_id,
+                       COALESCE(m.metadata->>'display_name', m.metadata->>'username', u.global_username) AS username,
+                       m.message_text AS message
+                FROM chat_messages m
+                LEFT JOIN users u ON u.user_id = m.user_id
+            "#,
+            at: "m.timestamp",
+            id: "m.message_id",
+            platform: "m.platform",
+            channel: "m.channel",
+        },
+        ExportKind::Events => Source {
+            select: r#"
+                SELECT e.event_id AS id, e.event_timestamp AS at, e.event_type, e.kind,
+                       e.data->>'platform' AS platform, e.data->>'channel' AS channel, e.data
+                FROM bot_events e
+            "#,
+            at: "e.event_timestamp",
+            id: "e.event_id",
+            platform: "e.data->>'platform'",
+            channel: "e.data->>'channel'",
+        },
+        ExportKind::Commands => Source {
+            select: r#"
+                SELECT cu.usage_id AS id, cu.executed_at AS at, cu.platform, cu.channel,
+                       c.command_name AS command, cu.user_id, cu.input_text AS input,
+                       cu.duration_ms, cu.error_message AS error
+                FROM command_usage cu
+                LEFT JOIN commands c ON c.command_id = cu.command_id
+            "#,
+            at: "cu.executed_at",
+            id: "cu.usage_id",
+            platform: "cu.platform",
+            channel: "cu.channel",
+        },
+    }
+}
+
+fn read_row(kind: ExportKind, r: &PgRow) -> Result<ExportRow, Error> {
+    let at: DateTime<Utc> = r.try_get("at")?;
+    let id: Uuid = r.try_get("id")?;
+    let text = |column: &str| -> Result<Value, Error> {
+        Ok(r.try_get::<Option<String>, _>(column)?.map(Value::from).unwrap_or(Value::Null))
+    };
+    let uuid = |column: &str| -> Result<Value, Error> {
+        Ok(r.try_get::<Option<Uuid>, _>(column)?.map(|u| Value::from(u.to_string())).unwrap_or(Value::Null))
+    };
+    let head = [Value::from(id.to_string()), Value::from(at.to_rfc3339())];
+    let rest = match kind {
+        ExportKind::Chat => vec![
+            text("platform")?, text("channel")?, uuid("user_id")?, text("username")?, text("message")?,
+        ],
+        ExportKind::Events => vec![
+            text("event_type")?,
+            text("kind")?,
+            text("platform")?,
+            text("channel")?,
+            r.try_get::<Option<Value>, _>("data")?.unwrap_or(Value::Null),
+        ],
+        ExportKind::Commands => vec![
+            text("platform")?,
+            text("channel")?,
+            text("command")?,
+            uuid("user_id")?,
+            text("input")?,
+            r.try_get::<Option<i32>, _>("duration_ms")?.map(Value::from).unwrap_or(Value::Null),
+            text("error")?,
+        ],
+    };
+    Ok(ExportRow { at, id, values: head.into_iter().chain(rest).collect() })
+}
+
+#[derive(Clone)]
+pub struct PostgresExportRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresExportRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    /// Appends the range, filters and (when paging) the keyset condition.
+    fn push_filters(
+        builder: &mut QueryBuilder<'_, Postgres>,
+        source: &Source,
+        query: &ExportQuery,
+        after: Option<(DateTime<Utc>, Uuid)>,
+    ) {
+        builder.push(" WHERE ").push(source.at).push(" >= ").push_bind(query.since)
+            .push(" AND ").push(source.at).push(" < ").push_bind(query.until);
+        if let Some(platform) = &query.platform {
+            builder.push(format!(" AND LOWER({}) = LOWER(", source.platform))
+                .push_bind(platform.clone())
+                .push(")");
+        }
+        if let Some(channel) = &query.channel {
+            builder.push(format!(" AND LOWER(LTRIM({}, '#')) = LOWER(", source.channel))
+                .push_bind(channel.trim_start_matches('#').to_string())
+                .push(")");
+        }
+        if let Some((at, id)) = after {
+            builder.push(format!(" AND ({}, {}) > (", source.at, source.id))
+                .push_bind(at)
+                .push(", ")
+                .push_bind(id)
+                .push(")");
+        }
+    }
+}
+
+#[async_trait]
+impl ExportRepository for PostgresExportRepository {
+    async fn count(&self, query: &ExportQuery) -> Result<u64, Error> {
+        let source = export_source(query.kind);
+        let mut builder = QueryBuilder::new(format!("SELECT COUNT(*) FROM ({}", source.select));
+        Self::push_filters(&mut builder, &source, query, None);
+        builder.push(") AS export_rows");
+        let count: i64 = builder.build().fetch_one(&self.pool).await?.try_get(0)?;
+        Ok(count.max(0) as u64)
+    }
+
+    async fn batch(&self, query: &ExportQuery, after: Option<(DateTime<Utc>, Uuid)>, limit: i64)
+        -> Result<Vec<ExportRow>, Error>
+    {
+        let source = export_source(query.kind);
+        let mut builder = QueryBuilder::new(source.select);
+        Self::push_filters(&mut builder, &source, query, after);
+        builder.push(format!(" ORDER BY {}, {} LIMIT ", source.at, source.id)).push_bind(limit);
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        rows.iter().map(|r| read_row(query.kind, r)).collect()
+    }
+}
@@ -2,11 +2,9 @@
 // File: maowbot-core/src/repositories/postgres/discord.rs
 // ========================================================
 use async_trait::async_trait;
-use chrono::Utc;
 use sqlx::{Pool, Postgres, Row, Transaction};
-use tracing::{debug, warn, info};
+use tracing::{warn, info};
 
-use sqlx::Executor;
 
 use maowbot_common::error::Error;
 use maowbot_common::models::discord::{
@@ -16,7 +14,6 @@ use maowbot_common::models::discord::{
     DiscordEventConfigRecord,
     DiscordLiveRoleRecord,
 };
-use maowbot_common::traits::repository_traits::DiscordRepository;
 
 #[derive(Clone)]
 pub struct PostgresDiscordRepository {
@@ -248,7 +245,7 @@ impl PostgresDiscordRepository {
 
     /// NEW: Dummy implementation for listing roles for a guild.
     /// In production, this should call the Discord API.
-    pub async fn list_roles_for_guild(&self, guild_id: &str) -> Result<Vec<(String, String)>, Error> {
+    pub async fn list_roles_for_guild(&self, _guild_id: &str) -> Result<Vec<(String, String)>, Error> {
         // For now, return an empty list.
         Ok(vec![])
     }
@@ -762,4 +759,40 @@ impl maowbot_common::traits::repository_traits::DiscordRepository for PostgresDi
     async fn list_live_roles(&self) -> Result<Vec<DiscordLiveRoleRecord>, Error> {
         self.list_live_roles().await
     }
+
+    async fn get_event_config_by_name(&self, event_name: &str) -> Result<Option<DiscordEventConfigRecord>, Error> {
+        self.get_event_config_by_name(event_name).await
+    }
+
+    async fn list_event_configs(&self) -> Result<Vec<DiscordEventConfigRecord>, Error> {
+        self.list_event_configs().await
+    }
+
+    async fn insert_event_config_multi(
+        &self,
+        event_name: &str,
+        guild_id: &str,
+        channel_id: &str,
+        respond_with_credential: Option<uuid::Uuid>,
+    ) -> Result<(), Error> {
+        self.insert_event_config_multi(event_name, guild_id, channel_id, respond_with_credential).await
+    }
+
+    async fn remove_event_config_multi(
+        &self,
+        event_name: &str,
+        guild_id: &str,
+        channel_id: &str,
+        respond_with_credential: Option<uuid::Uuid>,
+    ) -> Result<(), Error> {
+        self.remove_event_config_multi(event_name, guild_id, channel_id, respond_with_credential).await
+    }
+
+    async fn add_event_config_role(&self, event_name: &str, role_id: &str) -> Result<(), Error> {
+        self.add_event_config_role(event_name, role_id).await
+    }
+
+    async fn remove_event_config_role(&self, event_name: &str, role_id: &str) -> Result<(), Error> {
+        self.remove_event_config_role(event_name, role_id).await
+    }
 }
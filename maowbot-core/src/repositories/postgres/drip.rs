@@ -7,17 +7,19 @@ use std::sync::Mutex;
 use uuid::Uuid;
 use sqlx::{Pool, Postgres, Row};
 use crate::Error;
+use async_trait::async_trait;
 use maowbot_common::models::{DripAvatar, DripFitParam};
+pub use maowbot_common::traits::repository_traits::DripRepository;
 
 /// Drip repository with a reference to the Postgres pool, plus an optional
 /// in-memory "current avatar" concept to illustrate how you might track
 /// whichever avatar is actively worn.
-pub struct DripRepository {
+pub struct PostgresDripRepository {
     pool: Pool<Postgres>,
     current_avatar: Mutex<Option<DripAvatar>>,
 }
 
-impl DripRepository {
+impl PostgresDripRepository {
     /// Create a new repository, passing in the sqlx Postgres pool.
     pub fn new(pool: Pool<Postgres>) -> Self {
         Self {
@@ -488,3 +490,62 @@ impl DripRepository {
         Ok(())
     }
 }
+
+#[async_trait]
+impl DripRepository for PostgresDripRepository {
+    fn current_avatar(&self) -> Result<Option<DripAvatar>, Error> {
+        self.current_avatar()
+    }
+
+    async fn set_current_avatar(&self, user_id: Uuid, vrchat_avatar_id: &str, vrchat_avatar_name: &str) -> Result<(), Error> {
+        self.set_current_avatar(user_id, vrchat_avatar_id, vrchat_avatar_name).await
+    }
+
+    async fn add_prefix_rule_ignore(&self, avatar_id: &Uuid, prefix: &str) -> Result<(), Error> {
+        self.add_prefix_rule_ignore(avatar_id, prefix).await
+    }
+
+    async fn add_prefix_rule_strip(&self, avatar_id: &Uuid, prefix: &str) -> Result<(), Error> {
+        self.add_prefix_rule_strip(avatar_id, prefix).await
+    }
+
+    async fn update_local_avatar_name(&self, avatar_id: &Uuid, new_name: &str) -> Result<(), Error> {
+        self.update_local_avatar_name(avatar_id, new_name).await
+    }
+
+    async fn list_avatars(&self) -> Result<Vec<DripAvatar>, Error> {
+        self.list_avatars().await
+    }
+
+    async fn create_fit(&self, avatar_id: &Uuid, fit_name: &str) -> Result<(), Error> {
+        self.create_fit(avatar_id, fit_name).await
+    }
+
+    async fn add_fit_param(&self, fit_name: &str, param_name: &str, param_value: &str) -> Result<(), Error> {
+        self.add_fit_param(fit_name, param_name, param_value).await
+    }
+
+    async fn del_fit_param(&self, fit_name: &str, param_name: &str, param_value: &str) -> Result<(), Error> {
+        self.del_fit_param(fit_name, param_name, param_value).await
+    }
+
+    async fn get_fit_params(&self, fit_name: &str) -> Result<Vec<DripFitParam>, Error> {
+        self.get_fit_params(fit_name).await
+    }
+
+    async fn is_param_known_for_current_avatar(&self, param_name: String) -> Result<bool, Error> {
+        self.is_param_known_for_current_avatar(param_name).await
+    }
+
+    async fn add_prop_param(&self, prop_name: &str, param_name: &str, param_value: &str) -> Result<(), Error> {
+        self.add_prop_param(prop_name, param_name, param_value).await
+    }
+
+    async fn del_prop_param(&self, prop_name: &str, param_name: &str, param_value: &str) -> Result<(), Error> {
+        self.del_prop_param(prop_name, param_name, param_value).await
+    }
+
+    async fn add_prop_timer(&self, prop_name: &str, timer_data: &str) -> Result<(), Error> {
+        self.add_prop_timer(prop_name, timer_data).await
+    }
+}
@@ -8,11 +8,12 @@ use maowbot_common::models::event_pipeline::{
     EventPipeline, PipelineFilter, PipelineAction, PipelineExecutionLog,
     PipelineExecutionStatus, PipelineSharedData, EventTypeRegistry, EventHandlerRegistry,
     CreatePipelineRequest, UpdatePipelineRequest, CreateFilterRequest, CreateActionRequest,
-    HandlerType, ActionExecutionResult,
+    HandlerType, PipelineThrottle, ThrottleUsage,
 };
 use maowbot_common::traits::event_pipeline_traits::{
     EventPipelineRepository, PipelineExecutionLogRepository, PipelineSharedDataRepository,
     EventTypeRegistryRepository, EventHandlerRegistryRepository, EventPipelineSystemRepository,
+    PipelineThrottleRepository,
 };
 
 pub struct PostgresEventPipelineRepository {
@@ -620,7 +621,7 @@ impl EventPipelineRepository for PostgresEventPipelineRepository {
         Ok(())
     }
     
-    async fn get_pipelines_for_event(&self, event_type: &str, platform: &str) -> Result<Vec<EventPipeline>, Error> {
+    async fn get_pipelines_for_event(&self, _event_type: &str, _platform: &str) -> Result<Vec<EventPipeline>, Error> {
         // This would need to check filters to see which pipelines match
         // For now, return all enabled pipelines ordered by priority
         let rows = sqlx::query(
@@ -968,6 +969,133 @@ impl PipelineExecutionLogRepository for PostgresEventPipelineRepository {
         
         Ok(result.rows_affected() as i64)
     }
+
+    async fn count_failed_executions_since(&self, since: DateTime<Utc>) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pipeline_execution_log WHERE started_at >= $1 AND status IN ('failed', 'timeout')"
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+}
+
+fn throttle_from_row(row: &sqlx::postgres::PgRow) -> Result<PipelineThrottle, Error> {
+    Ok(PipelineThrottle {
+        pipeline_id: row.try_get("pipeline_id")?,
+        cooldown_seconds: row.try_get("cooldown_seconds")?,
+        user_cooldown_seconds: row.try_get("user_cooldown_seconds")?,
+        max_executions: row.try_get("max_executions")?,
+        max_executions_per_user: row.try_get("max_executions_per_user")?,
+        window_seconds: row.try_get("window_seconds")?,
+    })
+}
+
+#[async_trait]
+impl PipelineThrottleRepository for PostgresEventPipelineRepository {
+    async fn get_throttle(&self, pipeline_id: Uuid) -> Result<Option<PipelineThrottle>, Error> {
+        let row = sqlx::query("SELECT * FROM pipeline_throttles WHERE pipeline_id = $1")
+            .bind(pipeline_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(throttle_from_row).transpose()
+    }
+
+    async fn list_throttles(&self) -> Result<Vec<PipelineThrottle>, Error> {
+        let rows = sqlx::query("SELECT * FROM pipeline_throttles")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(throttle_from_row).collect()
+    }
+
+    async fn set_throttle(&self, throttle: &PipelineThrottle) -> Result<PipelineThrottle, Error> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO pipeline_throttles
+                (pipeline_id, cooldown_seconds, user_cooldown_seconds, max_executions,
+                 max_executions_per_user, window_seconds, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, NOW())
+            ON CONFLICT (pipeline_id) DO UPDATE SET
+                cooldown_seconds = EXCLUDED.cooldown_seconds,
+                user_cooldown_seconds = EXCLUDED.user_cooldown_seconds,
+                max_executions = EXCLUDED.max_executions,
+                max_executions_per_user = EXCLUDED.max_executions_per_user,
+                window_seconds = EXCLUDED.window_seconds,
+                updated_at = NOW()
+            RETURNING *
+            "#
+        )
+        .bind(throttle.pipeline_id)
+        .bind(throttle.cooldown_seconds)
+        .bind(throttle.user_cooldown_seconds)
+        .bind(throttle.max_executions)
+        .bind(throttle.max_executions_per_user)
+        .bind(throttle.window_seconds)
+        .fetch_one(&self.pool)
+        .await?;
+        throttle_from_row(&row)
+    }
+
+    async fn delete_throttle(&self, pipeline_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM pipeline_throttles WHERE pipeline_id = $1")
+            .bind(pipeline_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn throttle_usage(
+        &self,
+        pipeline_id: Uuid,
+        scope_key: &str,
+        window_since: DateTime<Utc>,
+    ) -> Result<ThrottleUsage, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE hit_at >= $3) AS in_window,
+                   MAX(hit_at) AS last_hit
+            FROM pipeline_throttle_hits
+            WHERE pipeline_id = $1 AND scope_key = $2
+            "#
+        )
+        .bind(pipeline_id)
+        .bind(scope_key)
+        .bind(window_since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(ThrottleUsage {
+            in_window: row.try_get("in_window")?,
+            last_hit: row.try_get("last_hit")?,
+        })
+    }
+
+    async fn record_throttle_hit(&self, pipeline_id: Uuid, scope_key: &str, at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query("INSERT INTO pipeline_throttle_hits (pipeline_id, scope_key, hit_at) VALUES ($1, $2, $3)")
+            .bind(pipeline_id)
+            .bind(scope_key)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn prune_throttle_hits(&self, pipeline_id: Uuid, older_than: DateTime<Utc>) -> Result<i64, Error> {
+        let result = sqlx::query("DELETE FROM pipeline_throttle_hits WHERE pipeline_id = $1 AND hit_at < $2")
+            .bind(pipeline_id)
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() as i64)
+    }
+
+    async fn reset_throttle_hits(&self, pipeline_id: Uuid) -> Result<i64, Error> {
+        let result = sqlx::query("DELETE FROM pipeline_throttle_hits WHERE pipeline_id = $1")
+            .bind(pipeline_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() as i64)
+    }
 }
 
 #[async_trait]
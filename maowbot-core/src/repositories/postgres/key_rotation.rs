@@ -0,0 +1,96 @@
+// File: maowbot-core/src/repositories/postgres/key_rotation.rs
+
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Row, Transaction};
+
+use crate::crypto::rotation::{KeyRotationRepository, RotationReport, StagedRotation};
+use crate::crypto::Encryptor;
+use crate::Error;
+
+/// An encrypted column: (table, row key expression, encrypted column, rows holding ciphertext).
+const ENCRYPTED_COLUMNS: &[(&str, &str, &str, &str)] = &[
+    ("platform_credentials", "credential_id", "primary_token", "TRUE"),
+    ("platform_credentials", "credential_id", "refresh_token", "TRUE"),
+    ("platform_credentials", "credential_id", "additional_data", "TRUE"),
+    ("ai_credentials", "credential_id", "api_key", "TRUE"),
+    ("obs_instances", "instance_number", "password_encrypted", "TRUE"),
+    ("bot_config", "(workspace_id::TEXT || '/' || config_key)", "config_value", "is_sensitive"),
+];
+
+#[derive(Clone)]
+pub struct PostgresKeyRotationRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresKeyRotationRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl KeyRotationRepository for PostgresKeyRotationRepository {
+    async fn begin_rotation(&self) -> Result<Box<dyn StagedRotation>, Error> {
+        Ok(Box::new(PostgresStagedRotation { tx: self.pool.begin().await? }))
+    }
+}
+
+struct PostgresStagedRotation {
+    tx: Transaction<'static, Postgres>,
+}
+
+#[async_trait]
+impl StagedRotation for PostgresStagedRotation {
+    async fn reencrypt_all(&mut self, old: &Encryptor, new: &Encryptor) -> Result<RotationReport, Error> {
+        reencrypt_columns(&mut self.tx, old, new).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), Error> {
+        Ok(self.tx.commit().await?)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), Error> {
+        Ok(self.tx.rollback().await?)
+    }
+}
+
+/// Decrypt every encrypted column with `old` and write it back encrypted with `new`.
+pub async fn reencrypt_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    old: &Encryptor,
+    new: &Encryptor,
+) -> Result<RotationReport, Error> {
+    let mut report = RotationReport::default();
+
+    for (table, id_column, column, filter) in ENCRYPTED_COLUMNS {
+        // Tables and columns come from the constant list above, never from input
+        let select = format!(
+            "SELECT {id}::TEXT AS id, {col} AS value FROM {table} WHERE {col} IS NOT NULL AND {filter} FOR UPDATE",
+            id = id_column, col = column, table = table, filter = filter
+        );
+        let rows = sqlx::query(&select).fetch_all(&mut **tx).await?;
+
+        let update = format!(
+            "UPDATE {table} SET {col} = $1 WHERE {id}::TEXT = $2",
+            table = table, col = column, id = id_column
+        );
+        let mut count = 0;
+        for row in rows {
+            let id: String = row.try_get("id")?;
+            let value: String = row.try_get("value")?;
+            let plaintext = old.decrypt(&value).map_err(|e| Error::Decryption(format!(
+                "{}.{} for {} does not decrypt with the current key ({}); nothing was changed",
+                table, column, id, e
+            )))?;
+            sqlx::query(&update)
+                .bind(new.encrypt(&plaintext)?)
+                .bind(&id)
+                .execute(&mut **tx)
+                .await?;
+            count += 1;
+        }
+        report.columns.push((format!("{}.{}", table, column), count));
+    }
+
+    Ok(report)
+}
@@ -6,6 +6,11 @@ pub mod autostart;
 pub mod link_requests;
 pub mod user_audit_log;
 pub mod user_analysis;
+pub mod analysis_runs;
+pub mod retention;
+pub mod ui_event_journal;
+pub mod data_export;
+pub mod key_rotation;
 pub mod bot_config;
 pub mod platform_config;
 pub mod commands;
@@ -17,4 +22,111 @@ pub mod discord;
 pub mod ai;
 pub mod osc_toggle;
 pub mod obs;
-pub mod event_pipeline;
\ No newline at end of file
+pub mod event_pipeline;
+pub mod workspaces;
+pub mod api_tokens;
+pub mod chat_archive;
+pub mod user_notes;
+pub mod donations;
+pub mod stream_markers;
+pub mod giveaways;
+pub mod protection;
+pub mod moderation_rules;
+pub mod emote_stats;
+pub mod clips;
+pub mod localization;
+pub mod redeem_schedules;
+pub mod midi_mappings;
+pub mod watchtime;
+pub mod responders;
+pub mod redeem_approvals;
+pub mod alert_rules;
+pub mod milestones;
+pub mod stream_sessions;
+
+use std::sync::Arc;
+
+use crate::crypto::Encryptor;
+use crate::db::Database;
+use super::{Repositories, WorkspaceRepos};
+
+impl Repositories {
+    /// Every repository over `db`'s pool.
+    pub fn postgres(db: &Database, encryptor: Encryptor) -> Self {
+        let pool = db.pool().clone();
+        let credentials = credentials::PostgresCredentialsRepository::new(pool.clone(), encryptor.clone());
+        let bot_config = bot_config::PostgresBotConfigRepository::new(pool.clone(), encryptor.clone());
+        let commands = commands::PostgresCommandRepository::new(pool.clone());
+        let redeems = redeems::PostgresRedeemRepository::new(pool.clone());
+        let pipelines = Arc::new(event_pipeline::PostgresEventPipelineRepository::new(pool.clone()));
+
+        Self {
+            users: Arc::new(user::UserRepository::new(pool.clone())),
+            identities: Arc::new(platform_identity::PlatformIdentityRepository::new(pool.clone())),
+            user_analysis: Arc::new(user_analysis::PostgresUserAnalysisRepository::new(pool.clone())),
+            user_notes: Arc::new(user_notes::PostgresUserNotesRepository::new(pool.clone())),
+            workspaces: Arc::new(workspaces::PostgresWorkspaceRepository::new(pool.clone())),
+            api_tokens: Arc::new(api_tokens::PostgresApiTokenRepository::new(pool.clone())),
+            credentials: Arc::new(credentials.clone()),
+            platform_configs: Arc::new(platform_config::PostgresPlatformConfigRepository::new(pool.clone())),
+            bot_config: Arc::new(bot_config.clone()),
+            autostart: Arc::new(autostart::PostgresAutostartRepository::new(pool.clone())),
+
+            analytics: Arc::new(analytics::PostgresAnalyticsRepository::new(pool.clone())),
+            chat_archive: Arc::new(chat_archive::PostgresChatArchiveRepository::new(pool.clone())),
+            commands: Arc::new(commands::PostgresCommandRepository::new(pool.clone())),
+            command_usage: Arc::new(command_usage::PostgresCommandUsageRepository::new(pool.clone())),
+            redeems: Arc::new(redeems::PostgresRedeemRepository::new(pool.clone())),
+            redeem_usage: Arc::new(redeem_usage::PostgresRedeemUsageRepository::new(pool.clone())),
+            redeem_schedules: Arc::new(redeem_schedules::PostgresRedeemScheduleRepository::new(pool.clone())),
+            redeem_approvals: Arc::new(redeem_approvals::PostgresRedeemApprovalRepository::new(pool.clone())),
+
+            discord: Arc::new(discord::PostgresDiscordRepository::new(pool.clone())),
+            obs: Arc::new(obs::PostgresObsRepository::new(pool.clone(), encryptor.clone())),
+            drip: Arc::new(drip::PostgresDripRepository::new(pool.clone())),
+            osc_toggles: Arc::new(osc_toggle::PostgresOscToggleRepository::new(pool.clone())),
+            midi_mappings: Arc::new(midi_mappings::PostgresMidiMappingRepository::new(pool.clone())),
+
+            pipelines: pipelines.clone(),
+            pipeline_logs: pipelines.clone(),
+            pipeline_system: pipelines,
+
+            ai_providers: Arc::new(ai::PostgresAiProviderRepository::new(pool.clone())),
+            ai_credentials: Arc::new(ai::PostgresAiCredentialRepository::new(pool.clone(), encryptor.clone())),
+            ai_models: Arc::new(ai::PostgresAiModelRepository::new(pool.clone())),
+            ai_triggers: Arc::new(ai::PostgresAiTriggerRepository::new(pool.clone())),
+            ai_memories: Arc::new(ai::PostgresAiMemoryRepository::new(pool.clone())),
+            ai_agents: Arc::new(ai::PostgresAiAgentRepository::new(pool.clone())),
+            ai_actions: Arc::new(ai::PostgresAiActionRepository::new(pool.clone())),
+            ai_prompts: Arc::new(ai::PostgresAiSystemPromptRepository::new(pool.clone())),
+            ai_configurations: Arc::new(ai::PostgresAiConfigurationRepository::new(pool.clone(), encryptor)),
+
+            donations: Arc::new(donations::PostgresDonationRepository::new(pool.clone())),
+            stream_markers: Arc::new(stream_markers::PostgresStreamMarkerRepository::new(pool.clone())),
+            stream_sessions: Arc::new(stream_sessions::PostgresStreamSessionRepository::new(pool.clone())),
+            giveaways: Arc::new(giveaways::PostgresGiveawayRepository::new(pool.clone())),
+            protection_incidents: Arc::new(protection::PostgresProtectionIncidentRepository::new(pool.clone())),
+            moderation_rules: Arc::new(moderation_rules::PostgresModerationRuleRepository::new(pool.clone())),
+            emote_stats: Arc::new(emote_stats::PostgresEmoteStatsRepository::new(pool.clone())),
+            clips: Arc::new(clips::PostgresClipRepository::new(pool.clone())),
+            localization: Arc::new(localization::PostgresLocalizationRepository::new(pool.clone())),
+            watchtime: Arc::new(watchtime::PostgresWatchtimeRepository::new(pool.clone())),
+            responders: Arc::new(responders::PostgresResponderRepository::new(pool.clone())),
+            alert_rules: Arc::new(alert_rules::PostgresAlertRuleRepository::new(pool.clone())),
+            milestones: Arc::new(milestones::PostgresMilestoneRepository::new(pool.clone())),
+
+            retention: Arc::new(retention::PostgresRetentionRepository::new(pool.clone())),
+            ui_journal: Arc::new(ui_event_journal::PostgresUiEventJournal::new(pool.clone())),
+            analysis_runs: Arc::new(analysis_runs::PostgresAnalysisRunRepository::new(pool.clone())),
+            exports: Arc::new(data_export::PostgresExportRepository::new(pool.clone())),
+            key_rotation: Arc::new(key_rotation::PostgresKeyRotationRepository::new(pool)),
+
+            scoped: Arc::new(move |ws| WorkspaceRepos {
+                credentials: Arc::new(credentials.for_workspace(ws)),
+                bot_config: Arc::new(bot_config.for_workspace(ws)),
+                commands: Arc::new(commands.for_workspace(ws)),
+                redeems: Arc::new(redeems.for_workspace(ws)),
+            }),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// File: maowbot-core/src/repositories/postgres/moderation_rules.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::ModerationRuleRepository;
+use maowbot_common::models::moderation_rule::ModerationRule;
+use crate::Error;
+
+const RULE_COLUMNS: &str = "rule_id, name, kind, pattern, severity, timeout_secs, permit_subs, \
+    permit_vips, permit_mods, category, enabled, hit_count, last_hit_at, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct PostgresModerationRuleRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresModerationRuleRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn rule_from_row(row: &PgRow) -> Result<ModerationRule, Error> {
+    let kind: String = row.try_get("kind")?;
+    let severity: String = row.try_get("severity")?;
+    Ok(ModerationRule {
+        rule_id: row.try_get("rule_id")?,
+        name: row.try_get("name")?,
+        kind: kind.parse()?,
+        pattern: row.try_get("pattern")?,
+        severity: severity.parse()?,
+        timeout_secs: row.try_get("timeout_secs")?,
+        permit_subs: row.try_get("permit_subs")?,
+        permit_vips: row.try_get("permit_vips")?,
+        permit_mods: row.try_get("permit_mods")?,
+        category: row.try_get("category")?,
+        enabled: row.try_get("enabled")?,
+        hit_count: row.try_get("hit_count")?,
+        last_hit_at: row.try_get("last_hit_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl ModerationRuleRepository for PostgresModerationRuleRepository {
+    async fn create_rule(&self, rule: &ModerationRule) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO moderation_rules ({RULE_COLUMNS}) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)"
+        ))
+            .bind(rule.rule_id)
+            .bind(&rule.name)
+            .bind(rule.kind.to_string())
+            .bind(&rule.pattern)
+            .bind(rule.severity.to_string())
+            .bind(rule.timeout_secs)
+            .bind(rule.permit_subs)
+            .bind(rule.permit_vips)
+            .bind(rule.permit_mods)
+            .bind(&rule.category)
+            .bind(rule.enabled)
+            .bind(rule.hit_count)
+            .bind(rule.last_hit_at)
+            .bind(rule.created_at)
+            .bind(rule.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_rule(&self, rule: &ModerationRule) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE moderation_rules
+            SET name = $2, kind = $3, pattern = $4, severity = $5, timeout_secs = $6,
+                permit_subs = $7, permit_vips = $8, permit_mods = $9, enabled = $10,
+                updated_at = $11, category = $12
+            WHERE rule_id = $1
+            "#
+        )
+            .bind(rule.rule_id)
+            .bind(&rule.name)
+            .bind(rule.kind.to_string())
+            .bind(&rule.pattern)
+            .bind(rule.severity.to_string())
+            .bind(rule.timeout_secs)
+            .bind(rule.permit_subs)
+            .bind(rule.permit_vips)
+            .bind(rule.permit_mods)
+            .bind(rule.enabled)
+            .bind(rule.updated_at)
+            .bind(&rule.category)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_rule(&self, rule_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM moderation_rules WHERE rule_id = $1")
+            .bind(rule_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_rule_by_name(&self, name: &str) -> Result<Option<ModerationRule>, Error> {
+        let row = sqlx::query(&format!("SELECT {RULE_COLUMNS} FROM moderation_rules WHERE LOWER(name) = LOWER($1)"))
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(rule_from_row).transpose()
+    }
+
+    async fn list_rules(&self) -> Result<Vec<ModerationRule>, Error> {
+        let rows = sqlx::query(&format!("SELECT {RULE_COLUMNS} FROM moderation_rules ORDER BY created_at"))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(rule_from_row).collect()
+    }
+
+    async fn record_hit(&self, rule_id: Uuid, at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE moderation_rules SET hit_count = hit_count + 1, last_hit_at = $2 WHERE rule_id = $1"
+        )
+            .bind(rule_id)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn reset_hits(&self, rule_id: Uuid) -> Result<(), Error> {
+        sqlx::query("UPDATE moderation_rules SET hit_count = 0, last_hit_at = NULL WHERE rule_id = $1")
+            .bind(rule_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
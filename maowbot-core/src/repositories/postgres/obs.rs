@@ -66,7 +66,8 @@ impl ObsRepository for PostgresObsRepository {
     }
     
     async fn update_instance(&self, instance: &ObsInstance) -> Result<(), Error> {
-        // Encrypt password before storing
+        // Encrypt password before storing; a key rotation waits until it's written
+        let _write = self.encryptor.write_guard().await;
         let encrypted_password = match &instance.password {
             Some(password) => Some(self.encryptor.encrypt(password)?),
             None => None,
@@ -1,10 +1,9 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 use maowbot_common::{
     error::Error,
-    models::osc_toggle::{OscTrigger, OscToggleState, OscAvatarConfig},
+    models::osc_toggle::{OscTrigger, OscToggleState, OscAvatarConfig, OscChatControl},
     traits::osc_toggle_traits::OscToggleRepository,
 };
 
@@ -18,6 +17,25 @@ impl PostgresOscToggleRepository {
     }
 }
 
+const CHAT_CONTROL_COLUMNS: &str = "id, name, parameter_name, parameter_type, min_role, \
+    min_value, max_value, cooldown_seconds, enabled, created_at, updated_at";
+
+fn chat_control_from_row(r: &sqlx::postgres::PgRow) -> Result<OscChatControl, Error> {
+    Ok(OscChatControl {
+        id: r.try_get("id")?,
+        name: r.try_get("name")?,
+        parameter_name: r.try_get("parameter_name")?,
+        parameter_type: r.try_get("parameter_type")?,
+        min_role: r.try_get("min_role")?,
+        min_value: r.try_get("min_value")?,
+        max_value: r.try_get("max_value")?,
+        cooldown_seconds: r.try_get("cooldown_seconds")?,
+        enabled: r.try_get("enabled")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+    })
+}
+
 #[async_trait]
 impl OscToggleRepository for PostgresOscToggleRepository {
     async fn get_trigger_by_id(&self, id: i32) -> Result<Option<OscTrigger>, Error> {
@@ -42,7 +60,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         if let Some(r) = row {
             let trigger = OscTrigger {
@@ -86,7 +104,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(redeem_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         if let Some(r) = row {
             let trigger = OscTrigger {
@@ -129,7 +147,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let mut triggers = Vec::new();
         for r in rows {
@@ -182,7 +200,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(trigger.enabled)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let result = OscTrigger {
             id: row.try_get("id")?,
@@ -232,7 +250,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(trigger.enabled)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let result = OscTrigger {
             id: row.try_get("id")?,
@@ -260,7 +278,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         Ok(())
     }
@@ -284,7 +302,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(user_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let mut toggles = Vec::new();
         for r in rows {
@@ -321,7 +339,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let mut toggles = Vec::new();
         for r in rows {
@@ -357,7 +375,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let mut toggles = Vec::new();
         for r in rows {
@@ -389,7 +407,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(state.user_id)
         .execute(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         // Now create the new active toggle
         let row = sqlx::query(
@@ -413,7 +431,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(state.expires_at)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let result = OscToggleState {
             id: row.try_get("id")?,
@@ -439,7 +457,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         Ok(())
     }
@@ -454,7 +472,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         )
         .execute(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         Ok(result.rows_affected() as i64)
     }
@@ -476,7 +494,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(avatar_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         if let Some(r) = row {
             let config = OscAvatarConfig {
@@ -517,7 +535,7 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         .bind(&config.parameter_mappings)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| Error::Database(e))?;
+        .map_err(Error::Database)?;
         
         let result = OscAvatarConfig {
             id: row.try_get("id")?,
@@ -530,4 +548,70 @@ impl OscToggleRepository for PostgresOscToggleRepository {
         
         Ok(result)
     }
-}
\ No newline at end of file
+
+    async fn list_chat_controls(&self) -> Result<Vec<OscChatControl>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {CHAT_CONTROL_COLUMNS} FROM osc_chat_controls ORDER BY name"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        rows.iter().map(chat_control_from_row).collect()
+    }
+
+    async fn get_chat_control(&self, name: &str) -> Result<Option<OscChatControl>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {CHAT_CONTROL_COLUMNS} FROM osc_chat_controls WHERE name = $1"
+        ))
+        .bind(name.to_lowercase())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        row.as_ref().map(chat_control_from_row).transpose()
+    }
+
+    async fn upsert_chat_control(&self, control: OscChatControl) -> Result<OscChatControl, Error> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO osc_chat_controls
+            (name, parameter_name, parameter_type, min_role, min_value, max_value, cooldown_seconds, enabled)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (name) DO UPDATE
+            SET parameter_name = EXCLUDED.parameter_name,
+                parameter_type = EXCLUDED.parameter_type,
+                min_role = EXCLUDED.min_role,
+                min_value = EXCLUDED.min_value,
+                max_value = EXCLUDED.max_value,
+                cooldown_seconds = EXCLUDED.cooldown_seconds,
+                enabled = EXCLUDED.enabled,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING {CHAT_CONTROL_COLUMNS}
+            "#
+        ))
+        .bind(control.name.to_lowercase())
+        .bind(&control.parameter_name)
+        .bind(&control.parameter_type)
+        .bind(control.min_role.to_lowercase())
+        .bind(control.min_value)
+        .bind(control.max_value)
+        .bind(control.cooldown_seconds)
+        .bind(control.enabled)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        chat_control_from_row(&row)
+    }
+
+    async fn delete_chat_control(&self, name: &str) -> Result<bool, Error> {
+        let result = sqlx::query("DELETE FROM osc_chat_controls WHERE name = $1")
+            .bind(name.to_lowercase())
+            .execute(&self.pool)
+            .await
+            .map_err(Error::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,121 @@
+// File: maowbot-core/src/repositories/postgres/redeem_approvals.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::RedeemApprovalRepository;
+use maowbot_common::models::redeem_approval::{ApprovalStatus, RedeemApproval};
+use crate::Error;
+
+const APPROVAL_COLUMNS: &str = "approval_id, redeem_id, reward_name, platform, channel, user_id, user_name, \
+    user_input, redemption, status, requested_at, decided_at, decided_by";
+
+#[derive(Clone)]
+pub struct PostgresRedeemApprovalRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresRedeemApprovalRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn approval_from_row(row: &PgRow) -> Result<RedeemApproval, Error> {
+    let status: String = row.try_get("status")?;
+    Ok(RedeemApproval {
+        approval_id: row.try_get("approval_id")?,
+        redeem_id: row.try_get("redeem_id")?,
+        reward_name: row.try_get("reward_name")?,
+        platform: row.try_get("platform")?,
+        channel: row.try_get("channel")?,
+        user_id: row.try_get("user_id")?,
+        user_name: row.try_get("user_name")?,
+        user_input: row.try_get("user_input")?,
+        redemption: row.try_get("redemption")?,
+        status: status.parse()?,
+        requested_at: row.try_get("requested_at")?,
+        decided_at: row.try_get("decided_at")?,
+        decided_by: row.try_get("decided_by")?,
+    })
+}
+
+#[async_trait]
+impl RedeemApprovalRepository for PostgresRedeemApprovalRepository {
+    async fn create_approval(&self, approval: &RedeemApproval) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO redeem_approvals ({APPROVAL_COLUMNS}) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)"
+        ))
+            .bind(approval.approval_id)
+            .bind(approval.redeem_id)
+            .bind(&approval.reward_name)
+            .bind(&approval.platform)
+            .bind(&approval.channel)
+            .bind(approval.user_id)
+            .bind(&approval.user_name)
+            .bind(&approval.user_input)
+            .bind(&approval.redemption)
+            .bind(approval.status.to_string())
+            .bind(approval.requested_at)
+            .bind(approval.decided_at)
+            .bind(&approval.decided_by)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_approval(&self, approval_id: Uuid) -> Result<Option<RedeemApproval>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {APPROVAL_COLUMNS} FROM redeem_approvals WHERE approval_id = $1"
+        ))
+            .bind(approval_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(approval_from_row).transpose()
+    }
+
+    async fn list_pending(&self) -> Result<Vec<RedeemApproval>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {APPROVAL_COLUMNS} FROM redeem_approvals WHERE status = 'pending' ORDER BY requested_at"
+        ))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(approval_from_row).collect()
+    }
+
+    async fn decide(
+        &self,
+        approval_id: Uuid,
+        status: ApprovalStatus,
+        decided_by: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<bool, Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE redeem_approvals
+            SET status = $2, decided_by = $3, decided_at = $4
+            WHERE approval_id = $1 AND status = 'pending'
+            "#,
+        )
+            .bind(approval_id)
+            .bind(status.to_string())
+            .bind(decided_by)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_pending_before(&self, before: DateTime<Utc>) -> Result<Vec<RedeemApproval>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {APPROVAL_COLUMNS} FROM redeem_approvals \
+             WHERE status = 'pending' AND requested_at < $1 ORDER BY requested_at"
+        ))
+            .bind(before)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(approval_from_row).collect()
+    }
+}
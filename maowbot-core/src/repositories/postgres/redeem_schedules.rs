@@ -0,0 +1,104 @@
+// File: maowbot-core/src/repositories/postgres/redeem_schedules.rs
+
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::RedeemScheduleRepository;
+use maowbot_common::models::redeem_schedule::RedeemSchedule;
+use crate::Error;
+
+const SCHEDULE_COLUMNS: &str = "schedule_id, redeem_id, name, condition_kind, condition_value, action, \
+    amount, priority, enabled, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct PostgresRedeemScheduleRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresRedeemScheduleRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn schedule_from_row(row: &PgRow) -> Result<RedeemSchedule, Error> {
+    let condition: String = row.try_get("condition_kind")?;
+    let action: String = row.try_get("action")?;
+    Ok(RedeemSchedule {
+        schedule_id: row.try_get("schedule_id")?,
+        redeem_id: row.try_get("redeem_id")?,
+        name: row.try_get("name")?,
+        condition: condition.parse()?,
+        condition_value: row.try_get("condition_value")?,
+        action: action.parse()?,
+        amount: row.try_get("amount")?,
+        priority: row.try_get("priority")?,
+        enabled: row.try_get("enabled")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl RedeemScheduleRepository for PostgresRedeemScheduleRepository {
+    async fn create_schedule(&self, schedule: &RedeemSchedule) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO redeem_schedules ({SCHEDULE_COLUMNS}) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+        ))
+            .bind(schedule.schedule_id)
+            .bind(schedule.redeem_id)
+            .bind(&schedule.name)
+            .bind(schedule.condition.to_string())
+            .bind(&schedule.condition_value)
+            .bind(schedule.action.to_string())
+            .bind(schedule.amount)
+            .bind(schedule.priority)
+            .bind(schedule.enabled)
+            .bind(schedule.created_at)
+            .bind(schedule.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_schedule(&self, schedule: &RedeemSchedule) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE redeem_schedules
+            SET name = $2, condition_kind = $3, condition_value = $4, action = $5, amount = $6,
+                priority = $7, enabled = $8, updated_at = $9
+            WHERE schedule_id = $1
+            "#
+        )
+            .bind(schedule.schedule_id)
+            .bind(&schedule.name)
+            .bind(schedule.condition.to_string())
+            .bind(&schedule.condition_value)
+            .bind(schedule.action.to_string())
+            .bind(schedule.amount)
+            .bind(schedule.priority)
+            .bind(schedule.enabled)
+            .bind(schedule.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_schedule(&self, schedule_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM redeem_schedules WHERE schedule_id = $1")
+            .bind(schedule_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_schedules(&self) -> Result<Vec<RedeemSchedule>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM redeem_schedules ORDER BY redeem_id, priority, created_at"
+        ))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(schedule_from_row).collect()
+    }
+}
@@ -3,18 +3,25 @@
 use async_trait::async_trait;
 use sqlx::{Pool, Postgres, Row};
 use uuid::Uuid;
-use chrono::Utc;
 use maowbot_common::error::Error;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
 use maowbot_common::models::redeem::{Redeem};
 use maowbot_common::traits::repository_traits::RedeemRepository;
 
+/// Channel point redeems for one workspace (the default workspace unless `for_workspace` is used).
 pub struct PostgresRedeemRepository {
     pub pool: Pool<Postgres>,
+    pub workspace_id: Uuid,
 }
 
 impl PostgresRedeemRepository {
     pub fn new(pool: Pool<Postgres>) -> Self {
-        Self { pool }
+        Self { pool, workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+
+    /// A repository over the same pool scoped to `workspace_id`.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { pool: self.pool.clone(), workspace_id }
     }
 }
 
@@ -39,9 +46,11 @@ impl RedeemRepository for PostgresRedeemRepository {
                 updated_at,
                 active_credential_id,
                 is_input_required,
-                redeem_prompt_text
+                redeem_prompt_text,
+                requires_approval,
+                workspace_id
             )
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16)
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18)
             "#,
         )
             .bind(rd.redeem_id)
@@ -60,6 +69,8 @@ impl RedeemRepository for PostgresRedeemRepository {
             .bind(rd.active_credential_id)
             .bind(rd.is_input_required)
             .bind(&rd.redeem_prompt_text)
+            .bind(rd.requires_approval)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
 
@@ -85,12 +96,15 @@ impl RedeemRepository for PostgresRedeemRepository {
                 updated_at,
                 active_credential_id,
                 is_input_required,
-                redeem_prompt_text
+                redeem_prompt_text,
+                requires_approval
             FROM redeems
             WHERE redeem_id = $1
+              AND workspace_id = $2
             "#,
         )
             .bind(redeem_id)
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -112,6 +126,7 @@ impl RedeemRepository for PostgresRedeemRepository {
                 active_credential_id: r.try_get("active_credential_id")?,
                 is_input_required: r.try_get("is_input_required").unwrap_or(false),
                 redeem_prompt_text: r.try_get("redeem_prompt_text")?,
+                requires_approval: r.try_get("requires_approval")?,
             };
             Ok(Some(rd))
         } else {
@@ -138,14 +153,17 @@ impl RedeemRepository for PostgresRedeemRepository {
                 updated_at,
                 active_credential_id,
                 is_input_required,
-                redeem_prompt_text
+                redeem_prompt_text,
+                requires_approval
             FROM redeems
             WHERE LOWER(platform) = LOWER($1)
               AND LOWER(reward_id) = LOWER($2)
+              AND workspace_id = $3
             "#,
         )
             .bind(platform)
             .bind(reward_id)
+            .bind(self.workspace_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -167,6 +185,7 @@ impl RedeemRepository for PostgresRedeemRepository {
                 active_credential_id: r.try_get("active_credential_id")?,
                 is_input_required: r.try_get("is_input_required").unwrap_or(false),
                 redeem_prompt_text: r.try_get("redeem_prompt_text")?,
+                requires_approval: r.try_get("requires_approval")?,
             };
             Ok(Some(rd))
         } else {
@@ -193,13 +212,16 @@ impl RedeemRepository for PostgresRedeemRepository {
                 updated_at,
                 active_credential_id,
                 is_input_required,
-                redeem_prompt_text
+                redeem_prompt_text,
+                requires_approval
             FROM redeems
             WHERE LOWER(platform) = LOWER($1)
+              AND workspace_id = $2
             ORDER BY reward_name ASC
             "#,
         )
             .bind(platform)
+            .bind(self.workspace_id)
             .fetch_all(&self.pool)
             .await?;
 
@@ -222,6 +244,7 @@ impl RedeemRepository for PostgresRedeemRepository {
                 active_credential_id: r.try_get("active_credential_id")?,
                 is_input_required: r.try_get("is_input_required").unwrap_or(false),
                 redeem_prompt_text: r.try_get("redeem_prompt_text")?,
+                requires_approval: r.try_get("requires_approval")?,
             };
             list.push(rd);
         }
@@ -246,8 +269,10 @@ impl RedeemRepository for PostgresRedeemRepository {
               updated_at = $11,
               active_credential_id = $12,
               is_input_required = $13,
-              redeem_prompt_text = $14
-            WHERE redeem_id = $15
+              redeem_prompt_text = $14,
+              requires_approval = $15
+            WHERE redeem_id = $16
+              AND workspace_id = $17
             "#,
         )
             .bind(&rd.platform)
@@ -264,15 +289,18 @@ impl RedeemRepository for PostgresRedeemRepository {
             .bind(rd.active_credential_id)
             .bind(rd.is_input_required)
             .bind(&rd.redeem_prompt_text)
+            .bind(rd.requires_approval)
             .bind(rd.redeem_id)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 
     async fn delete_redeem(&self, redeem_id: Uuid) -> Result<(), Error> {
-        sqlx::query("DELETE FROM redeems WHERE redeem_id = $1")
+        sqlx::query("DELETE FROM redeems WHERE redeem_id = $1 AND workspace_id = $2")
             .bind(redeem_id)
+            .bind(self.workspace_id)
             .execute(&self.pool)
             .await?;
         Ok(())
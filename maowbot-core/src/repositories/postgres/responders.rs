@@ -0,0 +1,133 @@
+// File: maowbot-core/src/repositories/postgres/responders.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::ResponderRepository;
+use maowbot_common::models::responder::Responder;
+use crate::Error;
+
+const RESPONDER_COLUMNS: &str = "responder_id, name, match_kind, pattern, responses, platform, channel, \
+    chance_percent, cooldown_secs, user_cooldown_secs, enabled, hit_count, last_hit_at, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct PostgresResponderRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresResponderRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn responder_from_row(row: &PgRow) -> Result<Responder, Error> {
+    let match_kind: String = row.try_get("match_kind")?;
+    Ok(Responder {
+        responder_id: row.try_get("responder_id")?,
+        name: row.try_get("name")?,
+        match_kind: match_kind.parse()?,
+        pattern: row.try_get("pattern")?,
+        responses: row.try_get("responses")?,
+        platform: row.try_get("platform")?,
+        channel: row.try_get("channel")?,
+        chance_percent: row.try_get("chance_percent")?,
+        cooldown_secs: row.try_get("cooldown_secs")?,
+        user_cooldown_secs: row.try_get("user_cooldown_secs")?,
+        enabled: row.try_get("enabled")?,
+        hit_count: row.try_get("hit_count")?,
+        last_hit_at: row.try_get("last_hit_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl ResponderRepository for PostgresResponderRepository {
+    async fn create_responder(&self, responder: &Responder) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO responders ({RESPONDER_COLUMNS}) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)"
+        ))
+            .bind(responder.responder_id)
+            .bind(&responder.name)
+            .bind(responder.match_kind.to_string())
+            .bind(&responder.pattern)
+            .bind(&responder.responses)
+            .bind(&responder.platform)
+            .bind(&responder.channel)
+            .bind(responder.chance_percent)
+            .bind(responder.cooldown_secs)
+            .bind(responder.user_cooldown_secs)
+            .bind(responder.enabled)
+            .bind(responder.hit_count)
+            .bind(responder.last_hit_at)
+            .bind(responder.created_at)
+            .bind(responder.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_responder(&self, responder: &Responder) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE responders
+            SET name = $2, match_kind = $3, pattern = $4, responses = $5, platform = $6,
+                channel = $7, chance_percent = $8, cooldown_secs = $9, user_cooldown_secs = $10,
+                enabled = $11, updated_at = $12
+            WHERE responder_id = $1
+            "#
+        )
+            .bind(responder.responder_id)
+            .bind(&responder.name)
+            .bind(responder.match_kind.to_string())
+            .bind(&responder.pattern)
+            .bind(&responder.responses)
+            .bind(&responder.platform)
+            .bind(&responder.channel)
+            .bind(responder.chance_percent)
+            .bind(responder.cooldown_secs)
+            .bind(responder.user_cooldown_secs)
+            .bind(responder.enabled)
+            .bind(responder.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_responder(&self, responder_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM responders WHERE responder_id = $1")
+            .bind(responder_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_responder_by_name(&self, name: &str) -> Result<Option<Responder>, Error> {
+        let row = sqlx::query(&format!("SELECT {RESPONDER_COLUMNS} FROM responders WHERE LOWER(name) = LOWER($1)"))
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(responder_from_row).transpose()
+    }
+
+    async fn list_responders(&self) -> Result<Vec<Responder>, Error> {
+        let rows = sqlx::query(&format!("SELECT {RESPONDER_COLUMNS} FROM responders ORDER BY created_at"))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(responder_from_row).collect()
+    }
+
+    async fn record_hit(&self, responder_id: Uuid, at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE responders SET hit_count = hit_count + 1, last_hit_at = $2 WHERE responder_id = $1"
+        )
+            .bind(responder_id)
+            .bind(at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
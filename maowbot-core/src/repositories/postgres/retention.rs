@@ -0,0 +1,90 @@
+// File: maowbot-core/src/repositories/postgres/retention.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+
+use crate::tasks::retention::{RetentionRepository, RetentionTarget};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct PostgresRetentionRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresRetentionRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RetentionRepository for PostgresRetentionRepository {
+    async fn count_rows(&self, target: RetentionTarget) -> Result<i64, Error> {
+        Ok(sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", target.table()))
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn prune_by_age(&self, target: RetentionTarget, max_age_days: i32, dry_run: bool) -> Result<u64, Error> {
+        let mut filter = format!(
+            "FROM {table} WHERE ($1::int > 0 AND {column} < NOW() - make_interval(days => $1::int))",
+            table = target.table(), column = target.time_column(),
+        );
+        if target == RetentionTarget::AiMemory {
+            filter.push_str(" OR expires_at < NOW()");
+        }
+        if dry_run {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", filter))
+                .bind(max_age_days)
+                .fetch_one(&self.pool)
+                .await?;
+            Ok(count as u64)
+        } else {
+            let res = sqlx::query(&format!("DELETE {}", filter))
+                .bind(max_age_days)
+                .execute(&self.pool)
+                .await?;
+            Ok(res.rows_affected())
+        }
+    }
+
+    async fn prune_by_size(&self, target: RetentionTarget, max_rows: i64, kept_age_days: i32, dry_run: bool)
+        -> Result<u64, Error>
+    {
+        let column = target.time_column();
+        let kept_by_age = format!("($2::int = 0 OR {} >= NOW() - make_interval(days => $2::int))", column);
+
+        let cutoff: Option<DateTime<Utc>> = sqlx::query_scalar(&format!(
+            "SELECT {column} FROM {table} WHERE {kept_by_age} ORDER BY {column} DESC OFFSET $1 LIMIT 1",
+            column = column, table = target.table(), kept_by_age = kept_by_age,
+        ))
+            .bind(max_rows)
+            .bind(kept_age_days)
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some(cutoff) = cutoff else { return Ok(0) };
+
+        // Rows sharing the cutoff timestamp go too, so slightly more than the
+        // excess can be removed; that beats keeping the table over its limit
+        let filter = format!(
+            "FROM {table} WHERE {column} <= $1 AND {kept_by_age}",
+            table = target.table(), column = column, kept_by_age = kept_by_age,
+        );
+        if dry_run {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", filter))
+                .bind(cutoff)
+                .bind(kept_age_days)
+                .fetch_one(&self.pool)
+                .await?;
+            Ok(count as u64)
+        } else {
+            let res = sqlx::query(&format!("DELETE {}", filter))
+                .bind(cutoff)
+                .bind(kept_age_days)
+                .execute(&self.pool)
+                .await?;
+            Ok(res.rows_affected())
+        }
+    }
+}
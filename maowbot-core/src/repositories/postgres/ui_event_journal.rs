@@ -0,0 +1,87 @@
+// File: maowbot-core/src/repositories/postgres/ui_event_journal.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use sqlx::{Pool, Postgres, Row};
+
+use crate::services::ui_events::{UiEvent, UiEventJournal, UiEventKind};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct PostgresUiEventJournal {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresUiEventJournal {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UiEventJournal for PostgresUiEventJournal {
+    async fn last_seq(&self) -> Result<Option<i64>, Error> {
+        Ok(sqlx::query_scalar("SELECT MAX(seq) FROM bot_events")
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn insert_events(&self, batch: &[UiEvent]) -> Result<(), Error> {
+        let seqs: Vec<i64> = batch.iter().map(|e| e.seq).collect();
+        let kinds: Vec<&str> = batch.iter().map(|e| e.kind.as_str()).collect();
+        let types: Vec<&str> = batch.iter().map(|e| e.event_type.as_str()).collect();
+        let timestamps: Vec<DateTime<Utc>> = batch.iter().map(|e| e.timestamp).collect();
+        let data: Vec<String> = batch.iter().map(|e| Value::Object(e.data.clone()).to_string()).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO bot_events (seq, kind, event_type, event_timestamp, data)
+            SELECT seq, kind, event_type, event_timestamp, data::jsonb
+            FROM UNNEST($1::bigint[], $2::text[], $3::text[], $4::timestamptz[], $5::text[])
+                AS t(seq, kind, event_type, event_timestamp, data)
+            ON CONFLICT (seq) DO NOTHING
+            "#,
+        )
+            .bind(&seqs)
+            .bind(&kinds)
+            .bind(&types)
+            .bind(&timestamps)
+            .bind(&data)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn events_between(&self, after: i64, before: i64, limit: i64) -> Result<Vec<UiEvent>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT seq, kind, event_type, event_timestamp, data
+            FROM bot_events
+            WHERE seq > $1 AND seq < $2
+            ORDER BY seq DESC
+            LIMIT $3
+            "#,
+        )
+            .bind(after)
+            .bind(before)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().filter_map(row_to_event).collect())
+    }
+}
+
+fn row_to_event(row: &sqlx::postgres::PgRow) -> Option<UiEvent> {
+    let kind: Option<String> = row.try_get("kind").ok()?;
+    let data: Option<Value> = row.try_get("data").ok()?;
+    Some(UiEvent {
+        seq: row.try_get("seq").ok()?,
+        kind: UiEventKind::parse(kind.as_deref()?)?,
+        event_type: row.try_get("event_type").ok()?,
+        timestamp: row.try_get("event_timestamp").ok()?,
+        data: match data {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        },
+    })
+}
@@ -1,13 +1,35 @@
 use crate::Error;
-use sqlx::{Pool, Postgres, Row};
+use sqlx::{Pool, Postgres, QueryBuilder, Row};
 use uuid::Uuid;
 use maowbot_common::models::user::User;
+pub use maowbot_common::models::user::{UserListQuery, UserSort};
 pub(crate) use maowbot_common::traits::repository_traits::UserRepo;
 
 pub struct UserRepository {
     pub pool: Pool<Postgres>,
 }
 
+fn push_user_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &UserListQuery) {
+    builder.push(" WHERE TRUE");
+    if query.active_only {
+        builder.push(" AND u.is_active");
+    }
+    if !query.platforms.is_empty() {
+        builder.push(" AND EXISTS (SELECT 1 FROM platform_identities pi WHERE pi.user_id = u.user_id AND LOWER(pi.platform) = ANY(")
+            .push_bind(query.platforms.iter().map(|p| p.to_lowercase()).collect::<Vec<_>>())
+            .push("))");
+    }
+    if !query.roles.is_empty() {
+        builder.push(" AND EXISTS (SELECT 1 FROM platform_identities pi WHERE pi.user_id = u.user_id AND pi.platform_roles ?| ")
+            .push_bind(query.roles.clone())
+            .push(")");
+    }
+    if let Some(prefix) = query.name_prefix.as_deref().filter(|p| !p.is_empty()) {
+        let escaped = prefix.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        builder.push(" AND LOWER(u.global_username) LIKE ").push_bind(format!("{}%", escaped));
+    }
+}
+
 impl UserRepository {
     pub fn new(pool: Pool<Postgres>) -> Self {
         Self { pool }
@@ -143,9 +165,41 @@ impl UserRepo for UserRepository {
 
         Ok(rows)
     }
+
+    async fn list_page(&self, query: &UserListQuery) -> Result<(Vec<User>, i64), Error> {
+        self.list_page(query).await
+    }
+
+    async fn merge_users(&self, primary_user_id: Uuid, duplicate_user_ids: Vec<Uuid>) -> Result<(), Error> {
+        self.merge_users(primary_user_id, duplicate_user_ids).await
+    }
 }
 
 impl UserRepository {
+    /// One page of users matching `query`, with the number of matches overall.
+    pub async fn list_page(&self, query: &UserListQuery) -> Result<(Vec<User>, i64), Error> {
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM users u");
+        push_user_filters(&mut count, query);
+        let total: i64 = count.build().fetch_one(&self.pool).await?.try_get(0)?;
+
+        let mut select = QueryBuilder::new(
+            "SELECT u.user_id, u.global_username, u.created_at, u.last_seen, u.is_active FROM users u"
+        );
+        push_user_filters(&mut select, query);
+        let column = match query.sort {
+            UserSort::CreatedAt => "u.created_at",
+            UserSort::LastSeen => "u.last_seen",
+            UserSort::Username => "LOWER(u.global_username)",
+        };
+        let direction = if query.descending { "DESC" } else { "ASC" };
+        // user_id breaks ties so pages don't overlap or skip rows
+        select.push(format!(" ORDER BY {} {} NULLS LAST, u.user_id {}", column, direction, direction))
+            .push(" LIMIT ").push_bind(query.limit.max(1))
+            .push(" OFFSET ").push_bind(query.offset.max(0));
+        let users = select.build_query_as::<User>().fetch_all(&self.pool).await?;
+        Ok((users, total))
+    }
+
     /// Find duplicate users based on similar usernames
     pub async fn find_duplicate_users(&self) -> Result<Vec<(String, Vec<User>)>, Error> {
         // Get all users with usernames
@@ -157,7 +211,7 @@ impl UserRepository {
         for user in users {
             if let Some(username) = &user.global_username {
                 let key = username.to_lowercase();
-                groups.entry(key).or_insert_with(Vec::new).push(user);
+                groups.entry(key).or_default().push(user);
             }
         }
         
@@ -212,13 +266,30 @@ impl UserRepository {
             
             // Update user audit logs
             sqlx::query(
-                "UPDATE user_audit_logs SET user_id = $1 WHERE user_id = $2"
+                "UPDATE user_audit_log SET user_id = $1 WHERE user_id = $2"
             )
                 .bind(primary_user_id)
                 .bind(dup_id)
                 .execute(&mut *tx)
                 .await?;
-            
+
+            // Move moderator notes and moderation history
+            sqlx::query(
+                "UPDATE user_notes SET user_id = $1 WHERE user_id = $2"
+            )
+                .bind(primary_user_id)
+                .bind(dup_id)
+                .execute(&mut *tx)
+                .await?;
+
+            sqlx::query(
+                "UPDATE user_moderation_actions SET user_id = $1 WHERE user_id = $2"
+            )
+                .bind(primary_user_id)
+                .bind(dup_id)
+                .execute(&mut *tx)
+                .await?;
+
             // Finally, delete the duplicate user
             sqlx::query(
                 "DELETE FROM users WHERE user_id = $1"
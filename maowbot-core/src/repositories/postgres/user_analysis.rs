@@ -33,10 +33,13 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
                 horni_score,
                 ai_notes,
                 moderator_notes,
+                bot_score,
+                bot_signals,
+                bot_scored_at,
                 created_at,
                 updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             "#,
         )
             .bind(analysis.user_analysis_id)
@@ -47,6 +50,9 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
             .bind(analysis.horni_score)
             .bind(&analysis.ai_notes)
             .bind(&analysis.moderator_notes)
+            .bind(analysis.bot_score)
+            .bind(&analysis.bot_signals)
+            .bind(analysis.bot_scored_at)
             .bind(analysis.created_at)
             .bind(analysis.updated_at)
             .execute(&self.pool)
@@ -66,6 +72,9 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
                    horni_score,
                    ai_notes,
                    moderator_notes,
+                   bot_score,
+                   bot_signals,
+                   bot_scored_at,
                    created_at,
                    updated_at
             FROM user_analysis
@@ -86,6 +95,9 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
                 horni_score: r.try_get("horni_score")?,
                 ai_notes: r.try_get("ai_notes")?,
                 moderator_notes: r.try_get("moderator_notes")?,
+                bot_score: r.try_get("bot_score")?,
+                bot_signals: r.try_get("bot_signals")?,
+                bot_scored_at: r.try_get("bot_scored_at")?,
                 created_at: r.try_get("created_at")?,
                 updated_at: r.try_get("updated_at")?,
             };
@@ -103,10 +115,7 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
         // You can adapt the logic to keep only the newest line, or
         // deduplicate by exact text, etc.
         // ----------------------------------------------------------------
-        let sanitized_notes = match &analysis.ai_notes {
-            Some(notes) => Some(deduplicate_ai_notes(notes)),
-            None => None,
-        };
+        let sanitized_notes = analysis.ai_notes.as_ref().map(|notes| deduplicate_ai_notes(notes));
 
         let now = Utc::now();
         sqlx::query(
@@ -118,8 +127,11 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
                 horni_score = $4,
                 ai_notes = $5,
                 moderator_notes = $6,
-                updated_at = $7
-            WHERE user_analysis_id = $8
+                bot_score = $7,
+                bot_signals = $8,
+                bot_scored_at = $9,
+                updated_at = $10
+            WHERE user_analysis_id = $11
             "#,
         )
             .bind(analysis.spam_score)
@@ -128,6 +140,9 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
             .bind(analysis.horni_score)
             .bind(&sanitized_notes)
             .bind(&analysis.moderator_notes)
+            .bind(analysis.bot_score)
+            .bind(&analysis.bot_signals)
+            .bind(analysis.bot_scored_at)
             .bind(now)
             .bind(analysis.user_analysis_id)
             .execute(&self.pool)
@@ -135,6 +150,38 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
 
         Ok(())
     }
+
+    async fn insert_history(&self, year_month: &str, scores: &UserAnalysis) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_analysis_history (
+                user_analysis_history_id,
+                user_id,
+                year_month,
+                spam_score,
+                intelligibility_score,
+                quality_score,
+                horni_score,
+                ai_notes,
+                created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+            .bind(Uuid::new_v4())
+            .bind(scores.user_id)
+            .bind(year_month)
+            .bind(scores.spam_score)
+            .bind(scores.intelligibility_score)
+            .bind(scores.quality_score)
+            .bind(scores.horni_score)
+            .bind(&scores.ai_notes)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
 }
 
 /// --------------------------------------------------------------------------
@@ -144,7 +191,7 @@ impl UserAnalysisRepository for PostgresUserAnalysisRepository {
 /// only one occurrence in the final output. All other lines remain unchanged.
 /// Adjust to your exact desired logic (e.g. keep only the last occurrence).
 /// --------------------------------------------------------------------------
-fn deduplicate_ai_notes(full_text: &str) -> String {
+pub(crate) fn deduplicate_ai_notes(full_text: &str) -> String {
     use std::collections::HashSet;
     let mut seen_summaries = HashSet::new();
     let mut result_lines = Vec::new();
@@ -0,0 +1,78 @@
+// File: maowbot-core/src/repositories/postgres/watchtime.rs
+
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+pub use maowbot_common::traits::repository_traits::WatchtimeRepository;
+use maowbot_common::models::watchtime::ViewerWatchtime;
+use crate::Error;
+
+const WATCHTIME_COLUMNS: &str = "channel, twitch_user_id, user_login, display_name, watch_seconds, \
+    first_seen, last_seen";
+
+#[derive(Clone)]
+pub struct PostgresWatchtimeRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresWatchtimeRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn watchtime_from_row(row: &PgRow) -> Result<ViewerWatchtime, Error> {
+    Ok(ViewerWatchtime {
+        channel: row.try_get("channel")?,
+        twitch_user_id: row.try_get("twitch_user_id")?,
+        user_login: row.try_get("user_login")?,
+        display_name: row.try_get("display_name")?,
+        watch_seconds: row.try_get("watch_seconds")?,
+        first_seen: row.try_get("first_seen")?,
+        last_seen: row.try_get("last_seen")?,
+    })
+}
+
+#[async_trait]
+impl WatchtimeRepository for PostgresWatchtimeRepository {
+    async fn add_watchtime(&self, entries: &[ViewerWatchtime]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for entry in entries {
+            sqlx::query(
+                r#"
+                INSERT INTO viewer_watchtime
+                    (channel, twitch_user_id, user_login, display_name, watch_seconds, first_seen, last_seen)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (channel, twitch_user_id) DO UPDATE
+                SET watch_seconds = viewer_watchtime.watch_seconds + EXCLUDED.watch_seconds,
+                    user_login = EXCLUDED.user_login,
+                    display_name = EXCLUDED.display_name,
+                    last_seen = EXCLUDED.last_seen
+                "#
+            )
+                .bind(&entry.channel)
+                .bind(&entry.twitch_user_id)
+                .bind(&entry.user_login)
+                .bind(&entry.display_name)
+                .bind(entry.watch_seconds)
+                .bind(entry.first_seen)
+                .bind(entry.last_seen)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_watchtime(&self, channel: &str, login: &str) -> Result<Option<ViewerWatchtime>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {WATCHTIME_COLUMNS} FROM viewer_watchtime \
+             WHERE channel = $1 AND user_login = LOWER($2) \
+             ORDER BY last_seen DESC LIMIT 1"
+        ))
+            .bind(channel)
+            .bind(login)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(watchtime_from_row).transpose()
+    }
+}
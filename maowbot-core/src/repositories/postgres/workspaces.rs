@@ -0,0 +1,156 @@
+// File: maowbot-core/src/repositories/postgres/workspaces.rs
+
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::workspace::{
+    channel_key, channel_platform, Workspace, WorkspaceChannel, DEFAULT_WORKSPACE_ID,
+};
+pub use maowbot_common::traits::repository_traits::WorkspaceRepository;
+
+#[derive(Clone)]
+pub struct PostgresWorkspaceRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresWorkspaceRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_workspace(r: &PgRow) -> Result<Workspace, Error> {
+    Ok(Workspace {
+        workspace_id: r.try_get("workspace_id")?,
+        name: r.try_get("name")?,
+        display_name: r.try_get("display_name")?,
+        created_at: r.try_get("created_at")?,
+    })
+}
+
+fn row_to_channel(r: &PgRow) -> Result<WorkspaceChannel, Error> {
+    Ok(WorkspaceChannel {
+        workspace_id: r.try_get("workspace_id")?,
+        platform: r.try_get("platform")?,
+        channel: r.try_get("channel")?,
+        created_at: r.try_get("created_at")?,
+    })
+}
+
+#[async_trait]
+impl WorkspaceRepository for PostgresWorkspaceRepository {
+    async fn create_workspace(&self, ws: &Workspace) -> Result<(), Error> {
+        if !Workspace::is_valid_name(&ws.name) {
+            return Err(Error::ValidationError(format!(
+                "Invalid workspace name '{}': use lowercase letters, digits, '-' and '_'", ws.name
+            )));
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO workspaces (workspace_id, name, display_name, created_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+            .bind(ws.workspace_id)
+            .bind(&ws.name)
+            .bind(&ws.display_name)
+            .bind(ws.created_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_workspace(&self, workspace_id: Uuid) -> Result<Option<Workspace>, Error> {
+        let row_opt = sqlx::query(
+            "SELECT workspace_id, name, display_name, created_at FROM workspaces WHERE workspace_id = $1"
+        )
+            .bind(workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_workspace).transpose()
+    }
+
+    async fn get_workspace_by_name(&self, name: &str) -> Result<Option<Workspace>, Error> {
+        let row_opt = sqlx::query(
+            "SELECT workspace_id, name, display_name, created_at FROM workspaces WHERE LOWER(name) = LOWER($1)"
+        )
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_workspace).transpose()
+    }
+
+    async fn list_workspaces(&self) -> Result<Vec<Workspace>, Error> {
+        let rows = sqlx::query(
+            "SELECT workspace_id, name, display_name, created_at FROM workspaces ORDER BY name ASC"
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_workspace).collect()
+    }
+
+    async fn delete_workspace(&self, workspace_id: Uuid) -> Result<(), Error> {
+        if workspace_id == DEFAULT_WORKSPACE_ID {
+            return Err(Error::ValidationError("The default workspace cannot be deleted".into()));
+        }
+        // The foreign key is RESTRICT too; this just explains why
+        let credentials: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM platform_credentials WHERE workspace_id = $1"
+        )
+            .bind(workspace_id)
+            .fetch_one(&self.pool)
+            .await?;
+        if credentials > 0 {
+            return Err(Error::ValidationError(format!(
+                "The workspace still has {} credential(s); remove them or move them to another workspace first",
+                credentials
+            )));
+        }
+        sqlx::query("DELETE FROM workspaces WHERE workspace_id = $1")
+            .bind(workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn assign_channel(&self, workspace_id: Uuid, platform: &str, channel: &str) -> Result<(), Error> {
+        let channel = channel_key(channel);
+        if channel.is_empty() {
+            return Err(Error::ValidationError("Channel name cannot be empty".into()));
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO workspace_channels (platform, channel, workspace_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (platform, channel)
+            DO UPDATE SET workspace_id = EXCLUDED.workspace_id, created_at = EXCLUDED.created_at
+            "#,
+        )
+            .bind(channel_platform(platform))
+            .bind(channel)
+            .bind(workspace_id)
+            .bind(chrono::Utc::now())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn unassign_channel(&self, platform: &str, channel: &str) -> Result<bool, Error> {
+        let result = sqlx::query("DELETE FROM workspace_channels WHERE platform = $1 AND channel = $2")
+            .bind(channel_platform(platform))
+            .bind(channel_key(channel))
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_channels(&self) -> Result<Vec<WorkspaceChannel>, Error> {
+        let rows = sqlx::query(
+            "SELECT platform, channel, workspace_id, created_at FROM workspace_channels ORDER BY platform, channel"
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_channel).collect()
+    }
+}
@@ -0,0 +1,116 @@
+// File: maowbot-core/src/repositories/sqlite/alert_rules.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, types::Json, Pool, Sqlite, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::AlertRuleRepository;
+use maowbot_common::models::alert_rule::{AlertKind, AlertRule};
+use crate::Error;
+
+const RULE_COLUMNS: &str = "rule_id, name, kinds, min_severity, route, target, dedupe_secs, quiet_hours, \
+    enabled, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct SqliteAlertRuleRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAlertRuleRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn rule_from_row(row: &SqliteRow) -> Result<AlertRule, Error> {
+    let Json(kinds): Json<Vec<String>> = row.try_get("kinds")?;
+    let min_severity: String = row.try_get("min_severity")?;
+    let route: String = row.try_get("route")?;
+    let quiet_hours: Option<String> = row.try_get("quiet_hours")?;
+    Ok(AlertRule {
+        rule_id: row.try_get("rule_id")?,
+        name: row.try_get("name")?,
+        kinds: kinds.iter().map(|k| k.parse()).collect::<Result<Vec<AlertKind>, _>>()?,
+        min_severity: min_severity.parse()?,
+        route: route.parse()?,
+        target: row.try_get("target")?,
+        dedupe_secs: row.try_get("dedupe_secs")?,
+        quiet_hours: quiet_hours.map(|q| q.parse()).transpose()?,
+        enabled: row.try_get("enabled")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn kind_names(rule: &AlertRule) -> Json<Vec<String>> {
+    Json(rule.kinds.iter().map(|k| k.to_string()).collect())
+}
+
+#[async_trait]
+impl AlertRuleRepository for SqliteAlertRuleRepository {
+    async fn create_rule(&self, rule: &AlertRule) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO alert_rules ({RULE_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+        ))
+            .bind(rule.rule_id)
+            .bind(&rule.name)
+            .bind(kind_names(rule))
+            .bind(rule.min_severity.to_string())
+            .bind(rule.route.to_string())
+            .bind(&rule.target)
+            .bind(rule.dedupe_secs)
+            .bind(rule.quiet_hours.map(|q| q.to_string()))
+            .bind(rule.enabled)
+            .bind(rule.created_at)
+            .bind(rule.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_rule(&self, rule: &AlertRule) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE alert_rules
+            SET name = ?2, kinds = ?3, min_severity = ?4, route = ?5, target = ?6,
+                dedupe_secs = ?7, quiet_hours = ?8, enabled = ?9, updated_at = ?10
+            WHERE rule_id = ?1
+            "#
+        )
+            .bind(rule.rule_id)
+            .bind(&rule.name)
+            .bind(kind_names(rule))
+            .bind(rule.min_severity.to_string())
+            .bind(rule.route.to_string())
+            .bind(&rule.target)
+            .bind(rule.dedupe_secs)
+            .bind(rule.quiet_hours.map(|q| q.to_string()))
+            .bind(rule.enabled)
+            .bind(rule.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_rule(&self, rule_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM alert_rules WHERE rule_id = ?1")
+            .bind(rule_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_rule_by_name(&self, name: &str) -> Result<Option<AlertRule>, Error> {
+        let row = sqlx::query(&format!("SELECT {RULE_COLUMNS} FROM alert_rules WHERE LOWER(name) = LOWER(?1)"))
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(rule_from_row).transpose()
+    }
+
+    async fn list_rules(&self) -> Result<Vec<AlertRule>, Error> {
+        let rows = sqlx::query(&format!("SELECT {RULE_COLUMNS} FROM alert_rules ORDER BY created_at"))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(rule_from_row).collect()
+    }
+}
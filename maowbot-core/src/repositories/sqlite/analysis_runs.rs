@@ -0,0 +1,119 @@
+// File: maowbot-core/src/repositories/sqlite/analysis_runs.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+use uuid::Uuid;
+
+use crate::tasks::analysis_recompute::{AnalysisRunRepository, RecomputeRun};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteAnalysisRunRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAnalysisRunRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AnalysisRunRepository for SqliteAnalysisRunRepository {
+    async fn mark_interrupted(&self) -> Result<u64, Error> {
+        let result = sqlx::query(
+            "UPDATE analysis_recompute_runs SET status = 'interrupted', updated_at = ?1 WHERE status = 'running'"
+        )
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn create_run(&self, since: DateTime<Utc>, until: DateTime<Utc>, total_users: i32) -> Result<RecomputeRun, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>(
+            r#"
+            INSERT INTO analysis_recompute_runs (run_id, since, until, status, total_users, started_at, updated_at)
+            VALUES (?1, ?2, ?3, 'running', ?4, ?5, ?5)
+            RETURNING *
+            "#,
+        )
+            .bind(Uuid::new_v4())
+            .bind(since)
+            .bind(until)
+            .bind(total_users)
+            .bind(Utc::now())
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn resume_latest(&self) -> Result<Option<RecomputeRun>, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>(
+            r#"
+            UPDATE analysis_recompute_runs
+            SET status = 'running', error = NULL, updated_at = ?1
+            WHERE run_id = (
+                SELECT run_id FROM analysis_recompute_runs
+                WHERE status IN ('cancelled', 'interrupted', 'failed')
+                ORDER BY started_at DESC
+                LIMIT 1
+            )
+            RETURNING *
+            "#,
+        )
+            .bind(Utc::now())
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn latest(&self) -> Result<Option<RecomputeRun>, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>(
+            "SELECT * FROM analysis_recompute_runs ORDER BY started_at DESC LIMIT 1"
+        )
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn get(&self, run_id: Uuid) -> Result<Option<RecomputeRun>, Error> {
+        Ok(sqlx::query_as::<_, RecomputeRun>("SELECT * FROM analysis_recompute_runs WHERE run_id = ?1")
+            .bind(run_id)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    async fn save_checkpoint(&self, run_id: Uuid, processed_users: i32, last_user_id: Uuid) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE analysis_recompute_runs
+            SET processed_users = ?2, last_user_id = ?3, updated_at = ?4
+            WHERE run_id = ?1
+            "#,
+        )
+            .bind(run_id)
+            .bind(processed_users)
+            .bind(last_user_id)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn finish(&self, run_id: Uuid, status: &str, error: Option<&str>) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE analysis_recompute_runs
+            SET status = ?2, error = ?3, updated_at = ?4,
+                finished_at = CASE WHEN ?2 = 'finished' THEN ?4 ELSE finished_at END
+            WHERE run_id = ?1
+            "#,
+        )
+            .bind(run_id)
+            .bind(status)
+            .bind(error)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+// File: maowbot-core/src/repositories/sqlite/autostart.rs
+
+use sqlx::{Pool, Sqlite};
+use async_trait::async_trait;
+use crate::Error;
+use crate::repositories::postgres::autostart::{AutostartEntry, AutostartRepository};
+
+#[derive(Clone)]
+pub struct SqliteAutostartRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAutostartRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AutostartRepository for SqliteAutostartRepository {
+    async fn get_enabled_entries(&self) -> Result<Vec<AutostartEntry>, Error> {
+        let rows = sqlx::query_as::<_, AutostartEntry>(
+            r#"
+            SELECT id, platform, account_name, enabled, created_at, updated_at
+            FROM autostart
+            WHERE enabled = true
+            ORDER BY platform, account_name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(rows)
+    }
+    
+    async fn get_all_entries(&self) -> Result<Vec<AutostartEntry>, Error> {
+        let rows = sqlx::query_as::<_, AutostartEntry>(
+            r#"
+            SELECT id, platform, account_name, enabled, created_at, updated_at
+            FROM autostart
+            ORDER BY platform, account_name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        Ok(rows)
+    }
+    
+    async fn set_autostart(&self, platform: &str, account_name: &str, enabled: bool) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO autostart (platform, account_name, enabled)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (platform, account_name)
+            DO UPDATE SET 
+                enabled = EXCLUDED.enabled,
+                updated_at = CURRENT_TIMESTAMP
+            "#
+        )
+        .bind(platform)
+        .bind(account_name)
+        .bind(enabled)
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    async fn remove_autostart(&self, platform: &str, account_name: &str) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM autostart
+            WHERE platform = ?1 AND account_name = ?2
+            "#
+        )
+        .bind(platform)
+        .bind(account_name)
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    async fn is_autostart_enabled(&self, platform: &str, account_name: &str) -> Result<bool, Error> {
+        let result: Option<(bool,)> = sqlx::query_as(
+            r#"
+            SELECT enabled
+            FROM autostart
+            WHERE platform = ?1 AND account_name = ?2
+            "#
+        )
+        .bind(platform)
+        .bind(account_name)
+        .fetch_optional(&self.pool)
+        .await?;
+        
+        Ok(result.map(|(enabled,)| enabled).unwrap_or(false))
+    }
+}
\ No newline at end of file
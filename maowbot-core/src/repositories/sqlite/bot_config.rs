@@ -0,0 +1,199 @@
+// File: maowbot-core/src/repositories/sqlite/bot_config.rs
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use sqlx::{Pool, Row, Sqlite};
+use uuid::Uuid;
+use crate::crypto::Encryptor;
+use maowbot_common::error::Error;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+pub use maowbot_common::traits::repository_traits::BotConfigRepository;
+
+/// Bot config for one workspace (the default workspace unless `for_workspace` is used).
+/// Rows flagged `is_sensitive` hold secrets encrypted with the master key.
+#[derive(Clone)]
+pub struct SqliteBotConfigRepository {
+    pool: Pool<Sqlite>,
+    encryptor: Encryptor,
+    workspace_id: Uuid,
+}
+
+impl SqliteBotConfigRepository {
+    pub fn new(pool: Pool<Sqlite>, encryptor: Encryptor) -> Self {
+        Self { pool, encryptor, workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+
+    /// A repository over the same pool that reads and writes `workspace_id`'s config.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { pool: self.pool.clone(), encryptor: self.encryptor.clone(), workspace_id }
+    }
+
+    pub fn workspace_id(&self) -> Uuid {
+        self.workspace_id
+    }
+}
+
+#[async_trait]
+impl BotConfigRepository for SqliteBotConfigRepository {
+    async fn get_callback_port(&self) -> Result<Option<u16>, Error> {
+        Ok(self.get_value("callback_port").await?.and_then(|v| v.parse::<u16>().ok()))
+    }
+
+    async fn set_callback_port(&self, port: u16) -> Result<(), Error> {
+        self.set_value("callback_port", &port.to_string()).await
+    }
+
+    async fn set_value(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        self.set_value_kv_meta(config_key, config_value, None).await
+    }
+
+    async fn get_value(&self, config_key: &str) -> Result<Option<String>, Error> {
+        let row_opt = sqlx::query(
+            "SELECT config_value FROM bot_config WHERE workspace_id = ?1 AND config_key = ?2 AND is_sensitive = 0 LIMIT 1"
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row_opt.map(|r| r.try_get("config_value")).transpose()?)
+    }
+
+    async fn list_all(&self) -> Result<Vec<(String, String)>, Error> {
+        let rows = sqlx::query("SELECT config_key, config_value FROM bot_config WHERE workspace_id = ?1 AND is_sensitive = 0")
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            out.push((row.try_get("config_key")?, row.try_get("config_value")?));
+        }
+        Ok(out)
+    }
+
+    async fn delete_value(&self, config_key: &str) -> Result<(), Error> {
+        sqlx::query("DELETE FROM bot_config WHERE workspace_id = ?1 AND config_key = ?2")
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_value_kv_meta(
+        &self,
+        config_key: &str,
+        config_value: &str,
+        config_meta: Option<JsonValue>
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO bot_config (workspace_id, config_key, config_value, config_meta)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (workspace_id, config_key)
+            DO UPDATE
+               SET config_value = excluded.config_value,
+                   config_meta  = excluded.config_meta,
+                   is_sensitive = 0,
+                   updated_at   = CURRENT_TIMESTAMP
+            "#,
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .bind(config_value)
+            .bind(config_meta.map(|m| m.to_string()))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_value_kv_meta(
+        &self,
+        config_key: &str,
+        config_value: &str
+    ) -> Result<Option<(String, Option<JsonValue>)>, Error> {
+        let row_opt = sqlx::query(
+            r#"
+            SELECT config_value, config_meta
+            FROM bot_config
+            WHERE workspace_id = ?1
+              AND config_key = ?2
+              AND config_value = ?3
+              AND is_sensitive = 0
+            "#,
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .bind(config_value)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row_opt {
+            Some(row) => {
+                let meta: Option<String> = row.try_get("config_meta")?;
+                let meta = meta.map(|m| serde_json::from_str(&m)).transpose()?;
+                Ok(Some((row.try_get("config_value")?, meta)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn delete_value_kv(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        sqlx::query(
+            "DELETE FROM bot_config WHERE workspace_id = ?1 AND config_key = ?2 AND config_value = ?3"
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .bind(config_value)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_secret(&self, config_key: &str, config_value: &str) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO bot_config (workspace_id, config_key, config_value, config_meta, is_sensitive)
+            VALUES (?1, ?2, ?3, NULL, 1)
+            ON CONFLICT (workspace_id, config_key)
+            DO UPDATE
+               SET config_value = excluded.config_value,
+                   config_meta  = NULL,
+                   is_sensitive = 1,
+                   updated_at   = CURRENT_TIMESTAMP
+            "#,
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .bind(self.encryptor.encrypt(config_value)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_secret(&self, config_key: &str) -> Result<Option<String>, Error> {
+        let row_opt = sqlx::query(
+            "SELECT config_value FROM bot_config WHERE workspace_id = ?1 AND config_key = ?2 AND is_sensitive = 1"
+        )
+            .bind(self.workspace_id)
+            .bind(config_key)
+            .fetch_optional(&self.pool)
+            .await?;
+        match row_opt {
+            Some(row) => Ok(Some(self.encryptor.decrypt(&row.try_get::<String, _>("config_value")?)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_secret_keys(&self) -> Result<Vec<String>, Error> {
+        let rows = sqlx::query("SELECT config_key FROM bot_config WHERE workspace_id = ?1 AND is_sensitive = 1")
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        let mut out = Vec::with_capacity(rows.len());
+        for row in rows {
+            out.push(row.try_get("config_key")?);
+        }
+        Ok(out)
+    }
+}
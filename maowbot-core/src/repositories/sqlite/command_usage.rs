@@ -0,0 +1,106 @@
+// File: maowbot-core/src/repositories/sqlite/command_usage.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Sqlite, Row};
+use uuid::Uuid;
+use maowbot_common::models::{CommandStats, CommandUsage};
+pub(crate) use maowbot_common::traits::repository_traits::CommandUsageRepository;
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteCommandUsageRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteCommandUsageRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+const USAGE_COLUMNS: &str = "usage_id, command_id, user_id, platform, executed_at, channel, \
+    input_text, metadata, duration_ms, error_message";
+
+fn usage_from_row(row: &SqliteRow) -> Result<CommandUsage, Error> {
+    Ok(CommandUsage {
+        usage_id: row.try_get("usage_id")?,
+        command_id: row.try_get("command_id")?,
+        // The user may have been deleted since
+        user_id: row.try_get::<Option<Uuid>, _>("user_id")?.unwrap_or_default(),
+        platform: row.try_get("platform")?,
+        used_at: row.try_get("executed_at")?,
+        channel: row.try_get::<Option<String>, _>("channel")?.unwrap_or_default(),
+        usage_text: row.try_get::<Option<String>, _>("input_text")?.unwrap_or_default(),
+        metadata: row.try_get("metadata")?,
+        duration_ms: row.try_get("duration_ms")?,
+        error_message: row.try_get("error_message")?,
+    })
+}
+
+#[async_trait]
+impl CommandUsageRepository for SqliteCommandUsageRepository {
+    async fn insert_usage(&self, usage: &CommandUsage) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO command_usage (
+                usage_id, command_id, user_id, platform, executed_at,
+                channel, input_text, metadata, duration_ms, error_message
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#,
+        )
+            .bind(usage.usage_id)
+            .bind(usage.command_id)
+            .bind(usage.user_id)
+            .bind(&usage.platform)
+            .bind(usage.used_at)
+            .bind(&usage.channel)
+            .bind(&usage.usage_text)
+            .bind(&usage.metadata)
+            .bind(usage.duration_ms)
+            .bind(&usage.error_message)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_usage_for_command(&self, command_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {USAGE_COLUMNS} FROM command_usage WHERE command_id = ?1 ORDER BY executed_at DESC LIMIT ?2"
+        ))
+            .bind(command_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(usage_from_row).collect()
+    }
+
+    async fn list_usage_for_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<CommandUsage>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {USAGE_COLUMNS} FROM command_usage WHERE user_id = ?1 ORDER BY executed_at DESC LIMIT ?2"
+        ))
+            .bind(user_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter().map(usage_from_row).collect()
+    }
+
+    async fn command_stats(&self, command_id: Uuid, since: DateTime<Utc>) -> Result<CommandStats, Error> {
+        // No percentile_cont here, so the window is aggregated in Rust
+        let rows = sqlx::query(&format!(
+            "SELECT {USAGE_COLUMNS} FROM command_usage WHERE command_id = ?1 AND executed_at >= ?2"
+        ))
+            .bind(command_id)
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await?;
+        let usages = rows.iter().map(usage_from_row).collect::<Result<Vec<_>, _>>()?;
+        Ok(CommandStats::from_usages(&usages, command_id, since))
+    }
+}
@@ -0,0 +1,188 @@
+// File: maowbot-core/src/repositories/sqlite/commands.rs
+
+use std::collections::HashMap;
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, types::Json, Pool, Row, Sqlite};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::command::Command;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::CommandRepository;
+
+const COMMAND_COLUMNS: &str = "command_id, platform, command_name, min_role, is_active, created_at, updated_at, \
+    cooldown_seconds, cooldown_warnonce, respond_with_credential, stream_online_only, stream_offline_only, \
+    active_credential_id, user_cooldown_seconds, role_cooldowns, cooldown_bypass_mods, reply_privately, reply_to_message, response";
+
+/// Commands for one workspace (the default workspace unless `for_workspace` is used).
+#[derive(Clone)]
+pub struct SqliteCommandRepository {
+    pool: Pool<Sqlite>,
+    workspace_id: Uuid,
+}
+
+impl SqliteCommandRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool, workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+
+    /// A repository over the same pool scoped to `workspace_id`.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { pool: self.pool.clone(), workspace_id }
+    }
+}
+
+fn row_to_command(r: &SqliteRow) -> Result<Command, Error> {
+    Ok(Command {
+        command_id: r.try_get("command_id")?,
+        platform: r.try_get("platform")?,
+        command_name: r.try_get("command_name")?,
+        min_role: r.try_get("min_role")?,
+        is_active: r.try_get("is_active")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+        cooldown_seconds: r.try_get("cooldown_seconds")?,
+        cooldown_warnonce: r.try_get("cooldown_warnonce")?,
+        respond_with_credential: r.try_get("respond_with_credential")?,
+        stream_online_only: r.try_get("stream_online_only")?,
+        stream_offline_only: r.try_get("stream_offline_only")?,
+        active_credential_id: r.try_get("active_credential_id")?,
+        user_cooldown_seconds: r.try_get("user_cooldown_seconds")?,
+        role_cooldowns: r.try_get::<Json<HashMap<String, i32>>, _>("role_cooldowns")?.0,
+        cooldown_bypass_mods: r.try_get("cooldown_bypass_mods")?,
+        reply_privately: r.try_get("reply_privately")?,
+        reply_to_message: r.try_get("reply_to_message")?,
+        response: r.try_get("response")?,
+    })
+}
+
+#[async_trait]
+impl CommandRepository for SqliteCommandRepository {
+    async fn create_command(&self, cmd: &Command) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO commands ({}, workspace_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)",
+            COMMAND_COLUMNS
+        ))
+            .bind(cmd.command_id)
+            .bind(&cmd.platform)
+            .bind(&cmd.command_name)
+            .bind(&cmd.min_role)
+            .bind(cmd.is_active)
+            .bind(cmd.created_at)
+            .bind(cmd.updated_at)
+            .bind(cmd.cooldown_seconds)
+            .bind(cmd.cooldown_warnonce)
+            .bind(cmd.respond_with_credential)
+            .bind(cmd.stream_online_only)
+            .bind(cmd.stream_offline_only)
+            .bind(cmd.active_credential_id)
+            .bind(cmd.user_cooldown_seconds)
+            .bind(Json(&cmd.role_cooldowns))
+            .bind(cmd.cooldown_bypass_mods)
+            .bind(cmd.reply_privately)
+            .bind(cmd.reply_to_message)
+            .bind(&cmd.response)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_command_by_id(&self, command_id: Uuid) -> Result<Option<Command>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM commands WHERE command_id = ?1 AND workspace_id = ?2", COMMAND_COLUMNS
+        ))
+            .bind(command_id)
+            .bind(self.workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_command).transpose()
+    }
+
+    async fn get_command_by_name(&self, platform: &str, command_name: &str) -> Result<Option<Command>, Error> {
+        let row_opt = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM commands
+            WHERE LOWER(platform) = LOWER(?1)
+              AND LOWER(command_name) = LOWER(?2)
+              AND workspace_id = ?3
+            "#,
+            COMMAND_COLUMNS
+        ))
+            .bind(platform)
+            .bind(command_name)
+            .bind(self.workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_command).transpose()
+    }
+
+    async fn list_commands(&self, platform: &str) -> Result<Vec<Command>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM commands WHERE LOWER(platform) = LOWER(?1) AND workspace_id = ?2 ORDER BY command_name ASC",
+            COMMAND_COLUMNS
+        ))
+            .bind(platform)
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_command).collect()
+    }
+
+    async fn update_command(&self, cmd: &Command) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE commands
+            SET platform = ?1,
+                command_name = ?2,
+                min_role = ?3,
+                is_active = ?4,
+                updated_at = ?5,
+                cooldown_seconds = ?6,
+                cooldown_warnonce = ?7,
+                respond_with_credential = ?8,
+                stream_online_only = ?9,
+                stream_offline_only = ?10,
+                active_credential_id = ?11,
+                user_cooldown_seconds = ?12,
+                role_cooldowns = ?13,
+                cooldown_bypass_mods = ?14,
+                reply_privately = ?15,
+                reply_to_message = ?16,
+                response = ?17
+            WHERE command_id = ?18
+              AND workspace_id = ?19
+            "#,
+        )
+            .bind(&cmd.platform)
+            .bind(&cmd.command_name)
+            .bind(&cmd.min_role)
+            .bind(cmd.is_active)
+            .bind(cmd.updated_at)
+            .bind(cmd.cooldown_seconds)
+            .bind(cmd.cooldown_warnonce)
+            .bind(cmd.respond_with_credential)
+            .bind(cmd.stream_online_only)
+            .bind(cmd.stream_offline_only)
+            .bind(cmd.active_credential_id)
+            .bind(cmd.user_cooldown_seconds)
+            .bind(Json(&cmd.role_cooldowns))
+            .bind(cmd.cooldown_bypass_mods)
+            .bind(cmd.reply_privately)
+            .bind(cmd.reply_to_message)
+            .bind(&cmd.response)
+            .bind(cmd.command_id)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_command(&self, command_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM commands WHERE command_id = ?1 AND workspace_id = ?2")
+            .bind(command_id)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+// File: maowbot-core/src/repositories/sqlite/credentials.rs
+
+use std::str::FromStr;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+use crate::crypto::Encryptor;
+use maowbot_common::error::Error;
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+const CREDENTIAL_COLUMNS: &str = "credential_id, platform, platform_id, credential_type, user_id, user_name, \
+    primary_token, refresh_token, additional_data, expires_at, created_at, updated_at, \
+    is_bot, is_teammate, is_broadcaster";
+
+/// Platform credentials, encrypted the same way as the Postgres repository.
+/// Sees every workspace unless narrowed with `for_workspace`.
+#[derive(Clone)]
+pub struct SqliteCredentialsRepository {
+    pool: Pool<Sqlite>,
+    encryptor: Encryptor,
+    /// `None` = all workspaces (new credentials go to the default workspace).
+    workspace_id: Option<Uuid>,
+}
+
+impl SqliteCredentialsRepository {
+    pub fn new(pool: Pool<Sqlite>, encryptor: Encryptor) -> Self {
+        Self { pool, encryptor, workspace_id: None }
+    }
+
+    /// A repository over the same pool that only sees `workspace_id`'s credentials.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { workspace_id: Some(workspace_id), ..self.clone() }
+    }
+
+    fn row_to_credential(&self, r: &SqliteRow) -> Result<PlatformCredential, Error> {
+        let refresh: Option<String> = r.try_get("refresh_token")?;
+        let data: Option<String> = r.try_get("additional_data")?;
+        Ok(PlatformCredential {
+            credential_id: r.try_get("credential_id")?,
+            platform: Platform::from_str(&r.try_get::<String, _>("platform")?)
+                .map_err(|e| Error::Platform(e.to_string()))?,
+            platform_id: r.try_get("platform_id")?,
+            credential_type: r.try_get::<String, _>("credential_type")?.parse()?,
+            user_id: r.try_get("user_id")?,
+            user_name: r.try_get("user_name")?,
+            primary_token: self.encryptor.decrypt(&r.try_get::<String, _>("primary_token")?)?,
+            refresh_token: refresh.map(|s| self.encryptor.decrypt(&s)).transpose()?,
+            additional_data: match data {
+                Some(enc) => Some(serde_json::from_str(&self.encryptor.decrypt(&enc)?)?),
+                None => None,
+            },
+            expires_at: r.try_get("expires_at")?,
+            created_at: r.try_get("created_at")?,
+            updated_at: r.try_get("updated_at")?,
+            is_bot: r.try_get("is_bot")?,
+            is_teammate: r.try_get("is_teammate")?,
+            is_broadcaster: r.try_get("is_broadcaster")?,
+        })
+    }
+
+    /// Encrypted (primary_token, refresh_token, additional_data).
+    fn encrypt_secrets(&self, creds: &PlatformCredential) -> Result<(String, Option<String>, Option<String>), Error> {
+        let token = self.encryptor.encrypt(&creds.primary_token)?;
+        let refresh = creds.refresh_token.as_ref()
+            .map(|t| self.encryptor.encrypt(t))
+            .transpose()?;
+        let data = creds.additional_data.as_ref()
+            .map(|d| self.encryptor.encrypt(&d.to_string()))
+            .transpose()?;
+        Ok((token, refresh, data))
+    }
+}
+
+#[async_trait]
+impl CredentialsRepository for SqliteCredentialsRepository {
+    async fn store_credentials(&self, creds: &PlatformCredential) -> Result<(), Error> {
+        let (token, refresh, data) = self.encrypt_secrets(creds)?;
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO platform_credentials ({}, workspace_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+            ON CONFLICT (platform, user_id) DO UPDATE
+               SET platform_id     = excluded.platform_id,
+                   user_name       = excluded.user_name,
+                   primary_token   = excluded.primary_token,
+                   refresh_token   = excluded.refresh_token,
+                   additional_data = excluded.additional_data,
+                   expires_at      = excluded.expires_at,
+                   updated_at      = excluded.updated_at,
+                   is_bot          = excluded.is_bot,
+                   is_teammate     = excluded.is_teammate,
+                   is_broadcaster  = excluded.is_broadcaster
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+            .bind(creds.credential_id)
+            .bind(creds.platform.to_string())
+            .bind(&creds.platform_id)
+            .bind(creds.credential_type.to_string())
+            .bind(creds.user_id)
+            .bind(&creds.user_name)
+            .bind(token)
+            .bind(refresh)
+            .bind(data)
+            .bind(creds.expires_at)
+            .bind(creds.created_at)
+            .bind(creds.updated_at)
+            .bind(creds.is_bot)
+            .bind(creds.is_teammate)
+            .bind(creds.is_broadcaster)
+            .bind(self.workspace_id.unwrap_or(DEFAULT_WORKSPACE_ID))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_credentials(&self, platform: &Platform, user_id: Uuid) -> Result<Option<PlatformCredential>, Error> {
+        let row_opt = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM platform_credentials
+            WHERE LOWER(platform) = LOWER(?1)
+              AND user_id = ?2
+              AND (?3 IS NULL OR workspace_id = ?3)
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+            .bind(platform.to_string())
+            .bind(user_id)
+            .bind(self.workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(|r| self.row_to_credential(r)).transpose()
+    }
+
+    async fn get_credential_by_id(&self, credential_id: Uuid) -> Result<Option<PlatformCredential>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM platform_credentials WHERE credential_id = ?1 AND (?2 IS NULL OR workspace_id = ?2) LIMIT 1",
+            CREDENTIAL_COLUMNS
+        ))
+            .bind(credential_id)
+            .bind(self.workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(|r| self.row_to_credential(r)).transpose()
+    }
+
+    async fn update_credentials(&self, creds: &PlatformCredential) -> Result<(), Error> {
+        let (token, refresh, data) = self.encrypt_secrets(creds)?;
+        sqlx::query(
+            r#"
+            UPDATE platform_credentials
+            SET platform_id     = ?1,
+                user_name       = ?2,
+                primary_token   = ?3,
+                refresh_token   = ?4,
+                additional_data = ?5,
+                expires_at      = ?6,
+                updated_at      = ?7,
+                is_bot          = ?8,
+                is_teammate     = ?9,
+                is_broadcaster  = ?10
+            WHERE LOWER(platform) = LOWER(?11)
+              AND user_id = ?12
+              AND (?13 IS NULL OR workspace_id = ?13)
+            "#,
+        )
+            .bind(&creds.platform_id)
+            .bind(&creds.user_name)
+            .bind(token)
+            .bind(refresh)
+            .bind(data)
+            .bind(creds.expires_at)
+            .bind(creds.updated_at)
+            .bind(creds.is_bot)
+            .bind(creds.is_teammate)
+            .bind(creds.is_broadcaster)
+            .bind(creds.platform.to_string())
+            .bind(creds.user_id)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_credentials(&self, platform: &Platform, user_id: Uuid) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM platform_credentials
+            WHERE LOWER(platform) = LOWER(?1)
+              AND user_id = ?2
+              AND (?3 IS NULL OR workspace_id = ?3)
+            "#,
+        )
+            .bind(platform.to_string())
+            .bind(user_id)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_expiring_credentials(&self, within: Duration) -> Result<Vec<PlatformCredential>, Error> {
+        // Timestamps are RFC 3339 text in UTC, so they compare correctly as strings
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM platform_credentials
+            WHERE expires_at IS NOT NULL
+              AND expires_at <= ?1
+              AND (?2 IS NULL OR workspace_id = ?2)
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+            .bind(Utc::now() + within)
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|r| self.row_to_credential(r)).collect()
+    }
+
+    async fn get_all_credentials(&self) -> Result<Vec<PlatformCredential>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM platform_credentials WHERE (?1 IS NULL OR workspace_id = ?1)",
+            CREDENTIAL_COLUMNS
+        ))
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|r| self.row_to_credential(r)).collect()
+    }
+
+    async fn list_credentials_for_platform(&self, platform: &Platform) -> Result<Vec<PlatformCredential>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM platform_credentials WHERE LOWER(platform) = LOWER(?1) AND (?2 IS NULL OR workspace_id = ?2)",
+            CREDENTIAL_COLUMNS
+        ))
+            .bind(platform.to_string())
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|r| self.row_to_credential(r)).collect()
+    }
+
+    async fn get_broadcaster_credential(&self, platform: &Platform) -> Result<Option<PlatformCredential>, Error> {
+        let all = self.list_credentials_for_platform(platform).await?;
+        Ok(all.into_iter().find(|c| c.is_broadcaster))
+    }
+
+    async fn get_bot_credentials(&self, platform: &Platform) -> Result<Option<PlatformCredential>, Error> {
+        let all = self.list_credentials_for_platform(platform).await?;
+        Ok(all.into_iter().find(|c| c.is_bot))
+    }
+
+    async fn get_teammate_credentials(&self, platform: &Platform) -> Result<Vec<PlatformCredential>, Error> {
+        let all = self.list_credentials_for_platform(platform).await?;
+        Ok(all.into_iter().filter(|c| c.is_teammate).collect())
+    }
+
+    async fn assign_workspace(&self, credential_id: Uuid, workspace_id: Uuid) -> Result<(), Error> {
+        sqlx::query("UPDATE platform_credentials SET workspace_id = ?1 WHERE credential_id = ?2")
+            .bind(workspace_id)
+            .bind(credential_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+// File: maowbot-core/src/repositories/sqlite/donations.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+pub use maowbot_common::traits::repository_traits::DonationRepository;
+use maowbot_common::models::donation::{Donation, DonorTotal};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteDonationRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteDonationRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn donation_from_row(row: &SqliteRow) -> Result<Donation, Error> {
+    let source: String = row.try_get("source")?;
+    Ok(Donation {
+        donation_id: row.try_get("donation_id")?,
+        source: source.parse()?,
+        external_id: row.try_get("external_id")?,
+        donor_name: row.try_get("donor_name")?,
+        message: row.try_get("message")?,
+        amount: row.try_get("amount")?,
+        currency: row.try_get("currency")?,
+        amount_base: row.try_get("amount_base")?,
+        base_currency: row.try_get("base_currency")?,
+        received_at: row.try_get("received_at")?,
+    })
+}
+
+#[async_trait]
+impl DonationRepository for SqliteDonationRepository {
+    async fn record_donation(&self, donation: &Donation) -> Result<bool, Error> {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO donations (
+                donation_id, source, external_id, donor_name, message,
+                amount, currency, amount_base, base_currency, received_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT (source, external_id) DO NOTHING
+            "#
+        )
+            .bind(donation.donation_id)
+            .bind(donation.source.to_string())
+            .bind(&donation.external_id)
+            .bind(&donation.donor_name)
+            .bind(&donation.message)
+            .bind(donation.amount)
+            .bind(&donation.currency)
+            .bind(donation.amount_base)
+            .bind(&donation.base_currency)
+            .bind(donation.received_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn list_recent(&self, limit: i64) -> Result<Vec<Donation>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT donation_id, source, external_id, donor_name, message,
+                   amount, currency, amount_base, base_currency, received_at
+            FROM donations
+            ORDER BY received_at DESC
+            LIMIT ?1
+            "#
+        )
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(donation_from_row).collect()
+    }
+
+    async fn top_donors(&self, since: Option<DateTime<Utc>>, limit: i64) -> Result<Vec<DonorTotal>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT MIN(donor_name) AS donor_name,
+                   SUM(amount_base) AS total,
+                   COUNT(*) AS donation_count
+            FROM donations
+            WHERE amount_base IS NOT NULL
+              AND (?1 IS NULL OR received_at >= ?1)
+            GROUP BY LOWER(donor_name)
+            ORDER BY total DESC
+            LIMIT ?2
+            "#
+        )
+            .bind(since)
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter()
+            .map(|row| Ok(DonorTotal {
+                donor_name: row.try_get("donor_name")?,
+                total: row.try_get("total")?,
+                donation_count: row.try_get("donation_count")?,
+            }))
+            .collect()
+    }
+}
@@ -0,0 +1,78 @@
+// File: maowbot-core/src/repositories/sqlite/emote_stats.rs
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{Pool, Sqlite, Row};
+pub use maowbot_common::traits::repository_traits::EmoteStatsRepository;
+use maowbot_common::models::emote_stats::{EmoteDailyCount, EmoteUsage};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteEmoteStatsRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteEmoteStatsRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EmoteStatsRepository for SqliteEmoteStatsRepository {
+    async fn add_daily_counts(&self, counts: &[EmoteDailyCount]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for count in counts {
+            sqlx::query(
+                r#"
+                INSERT INTO emote_usage_daily (channel, day, provider, emote_id, emote_name, uses)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ON CONFLICT (channel, day, provider, emote_id) DO UPDATE
+                SET uses = emote_usage_daily.uses + EXCLUDED.uses,
+                    emote_name = EXCLUDED.emote_name
+                "#
+            )
+                .bind(&count.usage.channel)
+                .bind(count.day)
+                .bind(count.usage.provider.to_string())
+                .bind(&count.usage.emote_id)
+                .bind(&count.usage.emote_name)
+                .bind(count.usage.uses)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn top_emotes(&self, channel: &str, since: NaiveDate, limit: i64) -> Result<Vec<EmoteUsage>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT provider, emote_id, MAX(emote_name) AS emote_name, SUM(uses) AS uses
+            FROM emote_usage_daily
+            WHERE channel = ?1 AND day >= ?2
+            GROUP BY provider, emote_id
+            ORDER BY uses DESC, emote_name
+            LIMIT ?3
+            "#
+        )
+            .bind(channel)
+            .bind(since)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                let provider: String = row.try_get("provider")?;
+                Ok(EmoteUsage {
+                    channel: channel.to_string(),
+                    provider: provider.parse()?,
+                    emote_id: row.try_get("emote_id")?,
+                    emote_name: row.try_get("emote_name")?,
+                    uses: row.try_get("uses")?,
+                })
+            })
+            .collect()
+    }
+}
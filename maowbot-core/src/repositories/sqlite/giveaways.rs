@@ -0,0 +1,197 @@
+// File: maowbot-core/src/repositories/sqlite/giveaways.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::GiveawayRepository;
+use maowbot_common::models::giveaway::{Giveaway, GiveawayEntry, GiveawayRules};
+use crate::Error;
+
+const GIVEAWAY_COLUMNS: &str = "giveaway_id, title, channel, keyword, points_cost, reward_id, \
+    sub_only, min_follow_days, exclude_winners_days, sub_weight, status, created_at, closed_at";
+
+const ENTRY_COLUMNS: &str = "entry_id, giveaway_id, user_id, username, is_subscriber, weight, \
+    is_winner, rerolled, entered_at, won_at";
+
+#[derive(Clone)]
+pub struct SqliteGiveawayRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteGiveawayRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn giveaway_from_row(row: &SqliteRow) -> Result<Giveaway, Error> {
+    let status: String = row.try_get("status")?;
+    Ok(Giveaway {
+        giveaway_id: row.try_get("giveaway_id")?,
+        title: row.try_get("title")?,
+        channel: row.try_get("channel")?,
+        keyword: row.try_get("keyword")?,
+        points_cost: row.try_get("points_cost")?,
+        reward_id: row.try_get("reward_id")?,
+        rules: GiveawayRules {
+            sub_only: row.try_get("sub_only")?,
+            min_follow_days: row.try_get("min_follow_days")?,
+            exclude_winners_days: row.try_get("exclude_winners_days")?,
+            sub_weight: row.try_get("sub_weight")?,
+        },
+        status: status.parse()?,
+        created_at: row.try_get("created_at")?,
+        closed_at: row.try_get("closed_at")?,
+    })
+}
+
+fn entry_from_row(row: &SqliteRow) -> Result<GiveawayEntry, Error> {
+    Ok(GiveawayEntry {
+        entry_id: row.try_get("entry_id")?,
+        giveaway_id: row.try_get("giveaway_id")?,
+        user_id: row.try_get("user_id")?,
+        username: row.try_get("username")?,
+        is_subscriber: row.try_get("is_subscriber")?,
+        weight: row.try_get("weight")?,
+        is_winner: row.try_get("is_winner")?,
+        rerolled: row.try_get("rerolled")?,
+        entered_at: row.try_get("entered_at")?,
+        won_at: row.try_get("won_at")?,
+    })
+}
+
+#[async_trait]
+impl GiveawayRepository for SqliteGiveawayRepository {
+    async fn create_giveaway(&self, giveaway: &Giveaway) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO giveaways ({GIVEAWAY_COLUMNS}) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+        ))
+            .bind(giveaway.giveaway_id)
+            .bind(&giveaway.title)
+            .bind(&giveaway.channel)
+            .bind(&giveaway.keyword)
+            .bind(giveaway.points_cost)
+            .bind(&giveaway.reward_id)
+            .bind(giveaway.rules.sub_only)
+            .bind(giveaway.rules.min_follow_days)
+            .bind(giveaway.rules.exclude_winners_days)
+            .bind(giveaway.rules.sub_weight)
+            .bind(giveaway.status.to_string())
+            .bind(giveaway.created_at)
+            .bind(giveaway.closed_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_giveaway(&self, giveaway: &Giveaway) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE giveaways
+            SET title = ?2, reward_id = ?3, status = ?4, closed_at = ?5
+            WHERE giveaway_id = ?1
+            "#
+        )
+            .bind(giveaway.giveaway_id)
+            .bind(&giveaway.title)
+            .bind(&giveaway.reward_id)
+            .bind(giveaway.status.to_string())
+            .bind(giveaway.closed_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_giveaway(&self, giveaway_id: Uuid) -> Result<Option<Giveaway>, Error> {
+        let row = sqlx::query(&format!("SELECT {GIVEAWAY_COLUMNS} FROM giveaways WHERE giveaway_id = ?1"))
+            .bind(giveaway_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(giveaway_from_row).transpose()
+    }
+
+    async fn list_open_giveaways(&self) -> Result<Vec<Giveaway>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {GIVEAWAY_COLUMNS} FROM giveaways WHERE status = 'open' ORDER BY created_at"
+        ))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(giveaway_from_row).collect()
+    }
+
+    async fn list_giveaways(&self, limit: i64) -> Result<Vec<Giveaway>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {GIVEAWAY_COLUMNS} FROM giveaways ORDER BY created_at DESC LIMIT ?1"
+        ))
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(giveaway_from_row).collect()
+    }
+
+    async fn add_entry(&self, entry: &GiveawayEntry) -> Result<bool, Error> {
+        let res = sqlx::query(&format!(
+            "INSERT INTO giveaway_entries ({ENTRY_COLUMNS}) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+             ON CONFLICT (giveaway_id, user_id) DO NOTHING"
+        ))
+            .bind(entry.entry_id)
+            .bind(entry.giveaway_id)
+            .bind(entry.user_id)
+            .bind(&entry.username)
+            .bind(entry.is_subscriber)
+            .bind(entry.weight)
+            .bind(entry.is_winner)
+            .bind(entry.rerolled)
+            .bind(entry.entered_at)
+            .bind(entry.won_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn list_entries(&self, giveaway_id: Uuid) -> Result<Vec<GiveawayEntry>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM giveaway_entries WHERE giveaway_id = ?1 ORDER BY entered_at"
+        ))
+            .bind(giveaway_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(entry_from_row).collect()
+    }
+
+    async fn update_entry(&self, entry: &GiveawayEntry) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE giveaway_entries
+            SET is_winner = ?2, rerolled = ?3, won_at = ?4
+            WHERE entry_id = ?1
+            "#
+        )
+            .bind(entry.entry_id)
+            .bind(entry.is_winner)
+            .bind(entry.rerolled)
+            .bind(entry.won_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn has_won_since(&self, user_id: Uuid, since: DateTime<Utc>) -> Result<bool, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM giveaway_entries
+                WHERE user_id = ?1 AND is_winner AND won_at >= ?2
+            ) AS won
+            "#
+        )
+            .bind(user_id)
+            .bind(since)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.try_get("won")?)
+    }
+}
@@ -0,0 +1,121 @@
+// File: maowbot-core/src/repositories/sqlite/localization.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+pub use maowbot_common::traits::repository_traits::LocalizationRepository;
+use maowbot_common::models::localization::{ChannelLanguage, LanguageString};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteLocalizationRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteLocalizationRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn string_from_row(row: &SqliteRow) -> Result<LanguageString, Error> {
+    Ok(LanguageString {
+        language: row.try_get("language")?,
+        key: row.try_get("message_key")?,
+        template: row.try_get("template")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn channel_from_row(row: &SqliteRow) -> Result<ChannelLanguage, Error> {
+    Ok(ChannelLanguage {
+        platform: row.try_get("platform")?,
+        channel: row.try_get("channel")?,
+        language: row.try_get("language")?,
+    })
+}
+
+#[async_trait]
+impl LocalizationRepository for SqliteLocalizationRepository {
+    async fn list_strings(&self) -> Result<Vec<LanguageString>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT language, message_key, template, updated_at
+            FROM language_strings
+            ORDER BY language, message_key
+            "#
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(string_from_row).collect()
+    }
+
+    async fn upsert_strings(&self, language: &str, strings: &[(String, String)]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for (key, template) in strings {
+            sqlx::query(
+                r#"
+                INSERT INTO language_strings (language, message_key, template, updated_at)
+                VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP)
+                ON CONFLICT (language, message_key) DO UPDATE
+                SET template = EXCLUDED.template,
+                    updated_at = EXCLUDED.updated_at
+                "#
+            )
+                .bind(language)
+                .bind(key)
+                .bind(template)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_string(&self, language: &str, key: &str) -> Result<bool, Error> {
+        let result = sqlx::query("DELETE FROM language_strings WHERE language = ?1 AND message_key = ?2")
+            .bind(language)
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_channel_languages(&self) -> Result<Vec<ChannelLanguage>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT platform, channel, language
+            FROM channel_languages
+            ORDER BY platform, channel
+            "#
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(channel_from_row).collect()
+    }
+
+    async fn set_channel_language(&self, channel: &ChannelLanguage) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO channel_languages (platform, channel, language)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (platform, channel) DO UPDATE
+            SET language = EXCLUDED.language
+            "#
+        )
+            .bind(&channel.platform)
+            .bind(&channel.channel)
+            .bind(&channel.language)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn clear_channel_language(&self, platform: &str, channel: &str) -> Result<bool, Error> {
+        let result = sqlx::query("DELETE FROM channel_languages WHERE platform = ?1 AND channel = ?2")
+            .bind(platform)
+            .bind(channel)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
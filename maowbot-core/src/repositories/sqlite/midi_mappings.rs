@@ -0,0 +1,117 @@
+// File: maowbot-core/src/repositories/sqlite/midi_mappings.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::MidiMappingRepository;
+use maowbot_common::models::midi_mapping::{MidiMapping, MidiTrigger};
+use crate::Error;
+
+const MAPPING_COLUMNS: &str = "mapping_id, name, trigger_kind, channel, number, action, target, \
+    argument, enabled, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct SqliteMidiMappingRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteMidiMappingRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn mapping_from_row(row: &SqliteRow) -> Result<MidiMapping, Error> {
+    let trigger_kind: String = row.try_get("trigger_kind")?;
+    let action: String = row.try_get("action")?;
+    let channel: i32 = row.try_get("channel")?;
+    let number: i32 = row.try_get("number")?;
+    Ok(MidiMapping {
+        mapping_id: row.try_get("mapping_id")?,
+        name: row.try_get("name")?,
+        trigger: MidiTrigger {
+            kind: trigger_kind.parse()?,
+            channel: channel as u8,
+            number: number as u8,
+        },
+        action: action.parse()?,
+        target: row.try_get("target")?,
+        argument: row.try_get("argument")?,
+        enabled: row.try_get("enabled")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl MidiMappingRepository for SqliteMidiMappingRepository {
+    async fn create_mapping(&self, mapping: &MidiMapping) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO midi_mappings ({MAPPING_COLUMNS}) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+        ))
+            .bind(mapping.mapping_id)
+            .bind(&mapping.name)
+            .bind(mapping.trigger.kind.to_string())
+            .bind(mapping.trigger.channel as i32)
+            .bind(mapping.trigger.number as i32)
+            .bind(mapping.action.to_string())
+            .bind(&mapping.target)
+            .bind(&mapping.argument)
+            .bind(mapping.enabled)
+            .bind(mapping.created_at)
+            .bind(mapping.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_mapping(&self, mapping: &MidiMapping) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE midi_mappings
+            SET name = ?2, trigger_kind = ?3, channel = ?4, number = ?5, action = ?6,
+                target = ?7, argument = ?8, enabled = ?9, updated_at = ?10
+            WHERE mapping_id = ?1
+            "#
+        )
+            .bind(mapping.mapping_id)
+            .bind(&mapping.name)
+            .bind(mapping.trigger.kind.to_string())
+            .bind(mapping.trigger.channel as i32)
+            .bind(mapping.trigger.number as i32)
+            .bind(mapping.action.to_string())
+            .bind(&mapping.target)
+            .bind(&mapping.argument)
+            .bind(mapping.enabled)
+            .bind(mapping.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_mapping(&self, mapping_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM midi_mappings WHERE mapping_id = ?1")
+            .bind(mapping_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_mapping_by_name(&self, name: &str) -> Result<Option<MidiMapping>, Error> {
+        let row = sqlx::query(&format!("SELECT {MAPPING_COLUMNS} FROM midi_mappings WHERE LOWER(name) = LOWER(?1)"))
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(mapping_from_row).transpose()
+    }
+
+    async fn list_mappings(&self) -> Result<Vec<MidiMapping>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {MAPPING_COLUMNS} FROM midi_mappings ORDER BY trigger_kind, channel, number"
+        ))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(mapping_from_row).collect()
+    }
+}
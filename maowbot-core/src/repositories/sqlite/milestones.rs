@@ -0,0 +1,140 @@
+// File: maowbot-core/src/repositories/sqlite/milestones.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+pub use maowbot_common::traits::repository_traits::MilestoneRepository;
+use maowbot_common::models::milestone::{MilestoneKind, SubHistory, ViewerMilestone};
+use crate::Error;
+
+const MILESTONE_COLUMNS: &str = "milestone_id, channel, twitch_user_id, user_login, display_name, \
+    kind, value, celebrated_at";
+
+#[derive(Clone)]
+pub struct SqliteMilestoneRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteMilestoneRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn milestone_from_row(row: &SqliteRow) -> Result<ViewerMilestone, Error> {
+    let kind: String = row.try_get("kind")?;
+    Ok(ViewerMilestone {
+        milestone_id: row.try_get("milestone_id")?,
+        channel: row.try_get("channel")?,
+        twitch_user_id: row.try_get("twitch_user_id")?,
+        user_login: row.try_get("user_login")?,
+        display_name: row.try_get("display_name")?,
+        kind: kind.parse::<MilestoneKind>()?,
+        value: row.try_get("value")?,
+        celebrated_at: row.try_get("celebrated_at")?,
+    })
+}
+
+#[async_trait]
+impl MilestoneRepository for SqliteMilestoneRepository {
+    async fn record_milestone(&self, milestone: &ViewerMilestone) -> Result<bool, Error> {
+        let result = sqlx::query(&format!(
+            "INSERT INTO viewer_milestones ({MILESTONE_COLUMNS}) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+             ON CONFLICT (channel, twitch_user_id, kind, value) DO NOTHING"
+        ))
+            .bind(milestone.milestone_id)
+            .bind(&milestone.channel)
+            .bind(&milestone.twitch_user_id)
+            .bind(&milestone.user_login)
+            .bind(&milestone.display_name)
+            .bind(milestone.kind.to_string())
+            .bind(milestone.value)
+            .bind(milestone.celebrated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_milestones(&self, limit: i64) -> Result<Vec<ViewerMilestone>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {MILESTONE_COLUMNS} FROM viewer_milestones ORDER BY celebrated_at DESC LIMIT ?1"
+        ))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(milestone_from_row).collect()
+    }
+
+    async fn get_sub_history(&self, channel: &str, twitch_user_id: &str) -> Result<Option<SubHistory>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT channel, twitch_user_id, cumulative_months, streak_months, updated_at
+            FROM viewer_sub_history
+            WHERE channel = ?1 AND twitch_user_id = ?2
+            "#
+        )
+            .bind(channel)
+            .bind(twitch_user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(|row| -> Result<SubHistory, Error> {
+            Ok(SubHistory {
+                channel: row.try_get("channel")?,
+                twitch_user_id: row.try_get("twitch_user_id")?,
+                cumulative_months: row.try_get("cumulative_months")?,
+                streak_months: row.try_get("streak_months")?,
+                updated_at: row.try_get("updated_at")?,
+            })
+        }).transpose()
+    }
+
+    async fn upsert_sub_history(&self, history: &SubHistory) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO viewer_sub_history (channel, twitch_user_id, cumulative_months, streak_months, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (channel, twitch_user_id) DO UPDATE
+            SET cumulative_months = EXCLUDED.cumulative_months,
+                streak_months = EXCLUDED.streak_months,
+                updated_at = EXCLUDED.updated_at
+            "#
+        )
+            .bind(&history.channel)
+            .bind(&history.twitch_user_id)
+            .bind(history.cumulative_months)
+            .bind(history.streak_months)
+            .bind(history.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_follow_date(&self, channel: &str, twitch_user_id: &str) -> Result<Option<DateTime<Utc>>, Error> {
+        let followed_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            "SELECT followed_at FROM viewer_follows WHERE channel = ?1 AND twitch_user_id = ?2"
+        )
+            .bind(channel)
+            .bind(twitch_user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(followed_at)
+    }
+
+    async fn upsert_follow_date(&self, channel: &str, twitch_user_id: &str, followed_at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO viewer_follows (channel, twitch_user_id, followed_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (channel, twitch_user_id) DO UPDATE
+            SET followed_at = EXCLUDED.followed_at
+            "#
+        )
+            .bind(channel)
+            .bind(twitch_user_id)
+            .bind(followed_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,449 @@
+//! SQLite implementations of the repository traits, compiled with the
+//! `sqlite` feature. The server runs on them when `--db-path` is a
+//! `sqlite://` URL (`Repositories::sqlite`), `--mode migrate-db` uses them
+//! to copy data to and from an embedded file, and `--memory-db` runs them
+//! on an in-memory database.
+//!
+//! Tables and column types follow `migrations_sqlite/`: UUIDs are BLOBs,
+//! timestamps RFC 3339 text and arrays JSON text.
+
+pub mod user;
+pub mod platform_identity;
+pub mod credentials;
+pub mod analytics;
+pub mod autostart;
+pub mod user_analysis;
+pub mod analysis_runs;
+pub mod retention;
+pub mod ui_event_journal;
+pub mod data_export;
+pub mod key_rotation;
+pub mod bot_config;
+pub mod platform_config;
+pub mod commands;
+pub mod command_usage;
+pub mod redeems;
+pub mod redeem_usage;
+pub mod drip;
+pub mod discord;
+pub mod ai;
+pub mod osc_toggle;
+pub mod obs;
+pub mod event_pipeline;
+pub mod workspaces;
+pub mod api_tokens;
+pub mod chat_archive;
+pub mod user_notes;
+pub mod donations;
+pub mod stream_markers;
+pub mod giveaways;
+pub mod protection;
+pub mod moderation_rules;
+pub mod emote_stats;
+pub mod clips;
+pub mod localization;
+pub mod redeem_schedules;
+pub mod midi_mappings;
+pub mod watchtime;
+pub mod responders;
+pub mod redeem_approvals;
+pub mod alert_rules;
+pub mod milestones;
+pub mod stream_sessions;
+
+use std::sync::Arc;
+
+use crate::crypto::Encryptor;
+use crate::db::sqlite::SqliteDatabase;
+use super::{Repositories, WorkspaceRepos};
+
+impl Repositories {
+    /// Every repository over `db`'s pool.
+    pub fn sqlite(db: &SqliteDatabase, encryptor: Encryptor) -> Self {
+        let pool = db.pool().clone();
+        let credentials = credentials::SqliteCredentialsRepository::new(pool.clone(), encryptor.clone());
+        let bot_config = bot_config::SqliteBotConfigRepository::new(pool.clone(), encryptor.clone());
+        let commands = commands::SqliteCommandRepository::new(pool.clone());
+        let redeems = redeems::SqliteRedeemRepository::new(pool.clone());
+        let pipelines = Arc::new(event_pipeline::SqliteEventPipelineRepository::new(pool.clone()));
+
+        Self {
+            users: Arc::new(user::SqliteUserRepository::new(pool.clone())),
+            identities: Arc::new(platform_identity::SqlitePlatformIdentityRepository::new(pool.clone())),
+            user_analysis: Arc::new(user_analysis::SqliteUserAnalysisRepository::new(pool.clone())),
+            user_notes: Arc::new(user_notes::SqliteUserNotesRepository::new(pool.clone())),
+            workspaces: Arc::new(workspaces::SqliteWorkspaceRepository::new(pool.clone())),
+            api_tokens: Arc::new(api_tokens::SqliteApiTokenRepository::new(pool.clone())),
+            credentials: Arc::new(credentials.clone()),
+            platform_configs: Arc::new(platform_config::SqlitePlatformConfigRepository::new(pool.clone())),
+            bot_config: Arc::new(bot_config.clone()),
+            autostart: Arc::new(autostart::SqliteAutostartRepository::new(pool.clone())),
+
+            analytics: Arc::new(analytics::SqliteAnalyticsRepository::new(pool.clone())),
+            chat_archive: Arc::new(chat_archive::SqliteChatArchiveRepository::new(pool.clone())),
+            commands: Arc::new(commands::SqliteCommandRepository::new(pool.clone())),
+            command_usage: Arc::new(command_usage::SqliteCommandUsageRepository::new(pool.clone())),
+            redeems: Arc::new(redeems::SqliteRedeemRepository::new(pool.clone())),
+            redeem_usage: Arc::new(redeem_usage::SqliteRedeemUsageRepository::new(pool.clone())),
+            redeem_schedules: Arc::new(redeem_schedules::SqliteRedeemScheduleRepository::new(pool.clone())),
+            redeem_approvals: Arc::new(redeem_approvals::SqliteRedeemApprovalRepository::new(pool.clone())),
+
+            discord: Arc::new(discord::SqliteDiscordRepository::new(pool.clone())),
+            obs: Arc::new(obs::SqliteObsRepository::new(pool.clone(), encryptor.clone())),
+            drip: Arc::new(drip::SqliteDripRepository::new(pool.clone())),
+            osc_toggles: Arc::new(osc_toggle::SqliteOscToggleRepository::new(pool.clone())),
+            midi_mappings: Arc::new(midi_mappings::SqliteMidiMappingRepository::new(pool.clone())),
+
+            pipelines: pipelines.clone(),
+            pipeline_logs: pipelines.clone(),
+            pipeline_system: pipelines,
+
+            ai_providers: Arc::new(ai::SqliteAiProviderRepository::new(pool.clone())),
+            ai_credentials: Arc::new(ai::SqliteAiCredentialRepository::new(pool.clone(), encryptor.clone())),
+            ai_models: Arc::new(ai::SqliteAiModelRepository::new(pool.clone())),
+            ai_triggers: Arc::new(ai::SqliteAiTriggerRepository::new(pool.clone())),
+            ai_memories: Arc::new(ai::SqliteAiMemoryRepository::new(pool.clone())),
+            ai_agents: Arc::new(ai::SqliteAiAgentRepository::new(pool.clone())),
+            ai_actions: Arc::new(ai::SqliteAiActionRepository::new(pool.clone())),
+            ai_prompts: Arc::new(ai::SqliteAiSystemPromptRepository::new(pool.clone())),
+            ai_configurations: Arc::new(ai::SqliteAiConfigurationRepository::new(pool.clone(), encryptor)),
+
+            donations: Arc::new(donations::SqliteDonationRepository::new(pool.clone())),
+            stream_markers: Arc::new(stream_markers::SqliteStreamMarkerRepository::new(pool.clone())),
+            stream_sessions: Arc::new(stream_sessions::SqliteStreamSessionRepository::new(pool.clone())),
+            giveaways: Arc::new(giveaways::SqliteGiveawayRepository::new(pool.clone())),
+            protection_incidents: Arc::new(protection::SqliteProtectionIncidentRepository::new(pool.clone())),
+            moderation_rules: Arc::new(moderation_rules::SqliteModerationRuleRepository::new(pool.clone())),
+            emote_stats: Arc::new(emote_stats::SqliteEmoteStatsRepository::new(pool.clone())),
+            clips: Arc::new(clips::SqliteClipRepository::new(pool.clone())),
+            localization: Arc::new(localization::SqliteLocalizationRepository::new(pool.clone())),
+            watchtime: Arc::new(watchtime::SqliteWatchtimeRepository::new(pool.clone())),
+            responders: Arc::new(responders::SqliteResponderRepository::new(pool.clone())),
+            alert_rules: Arc::new(alert_rules::SqliteAlertRuleRepository::new(pool.clone())),
+            milestones: Arc::new(milestones::SqliteMilestoneRepository::new(pool.clone())),
+
+            retention: Arc::new(retention::SqliteRetentionRepository::new(pool.clone())),
+            ui_journal: Arc::new(ui_event_journal::SqliteUiEventJournal::new(pool.clone())),
+            analysis_runs: Arc::new(analysis_runs::SqliteAnalysisRunRepository::new(pool.clone())),
+            exports: Arc::new(data_export::SqliteExportRepository::new(pool.clone())),
+            key_rotation: Arc::new(key_rotation::SqliteKeyRotationRepository::new(pool)),
+
+            scoped: Arc::new(move |ws| WorkspaceRepos {
+                credentials: Arc::new(credentials.for_workspace(ws)),
+                bot_config: Arc::new(bot_config.for_workspace(ws)),
+                commands: Arc::new(commands.for_workspace(ws)),
+                redeems: Arc::new(redeems.for_workspace(ws)),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use serde_json::{json, Map};
+    use uuid::Uuid;
+
+    use maowbot_common::models::alert_rule::{AlertKind, AlertRoute, AlertRule, AlertSeverity};
+    use maowbot_common::models::analytics::ChatMessage;
+    use maowbot_common::models::event_pipeline::{
+        CreatePipelineRequest, PipelineExecutionStatus, PipelineThrottle, UpdatePipelineRequest,
+    };
+    use maowbot_common::models::user::User;
+    use maowbot_common::traits::event_pipeline_traits::PipelineThrottleRepository;
+
+    use super::*;
+    use crate::crypto::Encryptor;
+    use crate::services::ui_events::{UiEvent, UiEventKind};
+    use crate::tasks::retention::RetentionTarget;
+
+    async fn migrated() -> (SqliteDatabase, Repositories) {
+        let db = SqliteDatabase::in_memory().await.unwrap();
+        db.migrate().await.unwrap();
+        let repos = Repositories::sqlite(&db, Encryptor::new(&[7u8; 32]).unwrap());
+        (db, repos)
+    }
+
+    async fn user(repos: &Repositories, name: &str) -> Uuid {
+        let now = Utc::now();
+        let user = User {
+            user_id: Uuid::new_v4(),
+            global_username: Some(name.to_string()),
+            created_at: now,
+            last_seen: now,
+            is_active: true,
+        };
+        repos.users.create(&user).await.unwrap();
+        user.user_id
+    }
+
+    fn pipeline(name: &str, tags: &[&str]) -> CreatePipelineRequest {
+        CreatePipelineRequest {
+            name: name.to_string(),
+            description: None,
+            enabled: true,
+            priority: 10,
+            stop_on_match: false,
+            stop_on_error: false,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipelines_keep_tags_and_log_results() {
+        let (db, repos) = migrated().await;
+        let greet = repos.pipelines.create_pipeline(&pipeline("greet", &["chat", "fun"])).await.unwrap();
+        repos.pipelines.create_pipeline(&pipeline("raid", &["alerts"])).await.unwrap();
+
+        let fun = repos.pipelines.list_pipelines_by_tag("fun").await.unwrap();
+        assert_eq!(fun.len(), 1);
+        assert_eq!(fun[0].tags, vec!["chat", "fun"]);
+
+        let update = UpdatePipelineRequest {
+            name: None, description: None, enabled: None, priority: None,
+            stop_on_match: None, stop_on_error: None, metadata: None,
+            tags: Some(vec!["quiet".to_string()]),
+        };
+        let updated = repos.pipelines.update_pipeline(greet.pipeline_id, &update).await.unwrap();
+        assert_eq!(updated.tags, vec!["quiet"]);
+        assert!(repos.pipelines.list_pipelines_by_tag("fun").await.unwrap().is_empty());
+
+        let run = repos.pipeline_logs.create_execution(greet.pipeline_id, "chat.message", json!({})).await.unwrap();
+        repos.pipeline_logs.add_action_result(run.execution_id, json!({"status": "success"})).await.unwrap();
+        repos.pipeline_logs.add_action_result(run.execution_id, json!({"status": "failed"})).await.unwrap();
+        repos.pipeline_logs
+            .update_execution_status(run.execution_id, PipelineExecutionStatus::Success, None)
+            .await
+            .unwrap();
+        let (executed, succeeded, results, duration): (i32, i32, String, Option<i64>) = sqlx::query_as(
+            "SELECT actions_executed, actions_succeeded, action_results, duration_ms
+             FROM pipeline_execution_log WHERE execution_id = ?1"
+        )
+            .bind(run.execution_id)
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!((executed, succeeded), (2, 1));
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&results).unwrap().as_array().unwrap().len(), 2);
+        assert!(duration.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_throttle_usage_counts_hits_in_the_window() {
+        let (db, repos) = migrated().await;
+        let p = repos.pipelines.create_pipeline(&pipeline("limited", &[])).await.unwrap();
+        let throttles = event_pipeline::SqliteEventPipelineRepository::new(db.pool().clone());
+        throttles.set_throttle(&PipelineThrottle {
+            pipeline_id: p.pipeline_id,
+            cooldown_seconds: 5,
+            user_cooldown_seconds: 0,
+            max_executions: 3,
+            max_executions_per_user: 0,
+            window_seconds: 60,
+        }).await.unwrap();
+
+        let now = Utc::now();
+        throttles.record_throttle_hit(p.pipeline_id, "", now - Duration::minutes(5)).await.unwrap();
+        throttles.record_throttle_hit(p.pipeline_id, "", now - Duration::seconds(10)).await.unwrap();
+        let usage = throttles.throttle_usage(p.pipeline_id, "", now - Duration::seconds(60)).await.unwrap();
+        assert_eq!(usage.in_window, 1);
+        assert_eq!(usage.last_hit.unwrap().timestamp(), (now - Duration::seconds(10)).timestamp());
+        assert_eq!(throttles.list_throttles().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ui_journal_round_trips_and_retention_prunes() {
+        let (_db, repos) = migrated().await;
+        let old = Utc::now() - Duration::days(40);
+        let batch: Vec<UiEvent> = (1..=3).map(|seq| UiEvent {
+            seq,
+            kind: UiEventKind::Chat,
+            event_type: "chat.message".into(),
+            timestamp: if seq == 1 { old } else { Utc::now() },
+            data: Map::from_iter([("user".to_string(), json!("maow"))]),
+        }).collect();
+        repos.ui_journal.insert_events(&batch).await.unwrap();
+        // Already journaled seqs are skipped
+        repos.ui_journal.insert_events(&batch[..1]).await.unwrap();
+        assert_eq!(repos.ui_journal.last_seq().await.unwrap(), Some(3));
+        let events = repos.ui_journal.events_between(0, 10, 10).await.unwrap();
+        assert_eq!(events.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(events[0].data["user"], json!("maow"));
+
+        let journal = RetentionTarget::EventJournal;
+        assert_eq!(repos.retention.prune_by_age(journal, 30, true).await.unwrap(), 1);
+        assert_eq!(repos.retention.prune_by_age(journal, 30, false).await.unwrap(), 1);
+        assert_eq!(repos.retention.prune_by_size(journal, 1, 0, false).await.unwrap(), 1);
+        assert_eq!(repos.retention.count_rows(journal).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chat_archive_purges_by_channel_retention() {
+        let (_db, repos) = migrated().await;
+        let viewer = user(&repos, "viewer").await;
+        let message = |channel: &str, days_old: i64| ChatMessage {
+            message_id: Uuid::new_v4(),
+            platform: "twitch-irc".into(),
+            channel: channel.into(),
+            user_id: viewer,
+            message_text: "hello there".into(),
+            timestamp: Utc::now() - Duration::days(days_old),
+            metadata: None,
+        };
+        repos.analytics.insert_chat_messages(&[
+            message("#short", 5),
+            message("#short", 1),
+            message("#long", 20),
+        ]).await.unwrap();
+        repos.chat_archive.set_retention_policy("twitch-irc", "#short", 3).await.unwrap();
+
+        assert_eq!(repos.chat_archive.count_expired_messages(30).await.unwrap(), 1);
+        assert_eq!(repos.chat_archive.purge_expired_messages(30).await.unwrap(), 1);
+        assert_eq!(repos.analytics.get_recent_messages("twitch-irc", "#short", 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_analysis_runs_resume_and_finish() {
+        let (_db, repos) = migrated().await;
+        let now = Utc::now();
+        let run = repos.analysis_runs.create_run(now - Duration::days(7), now, 4).await.unwrap();
+        assert_eq!(repos.analysis_runs.mark_interrupted().await.unwrap(), 1);
+        let resumed = repos.analysis_runs.resume_latest().await.unwrap().unwrap();
+        assert_eq!((resumed.run_id, resumed.status.as_str()), (run.run_id, "running"));
+
+        repos.analysis_runs.save_checkpoint(run.run_id, 2, Uuid::new_v4()).await.unwrap();
+        repos.analysis_runs.finish(run.run_id, "finished", None).await.unwrap();
+        let done = repos.analysis_runs.get(run.run_id).await.unwrap().unwrap();
+        assert_eq!(done.processed_users, 2);
+        assert!(done.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_discord_event_roles_are_a_set() {
+        let (db, _repos) = migrated().await;
+        let discord = discord::SqliteDiscordRepository::new(db.pool().clone());
+        discord.upsert_event_config("stream.online", "guild", "channel", None).await.unwrap();
+        discord.add_event_config_role("stream.online", "r1").await.unwrap();
+        discord.add_event_config_role("stream.online", "r2").await.unwrap();
+        discord.add_event_config_role("stream.online", "r1").await.unwrap();
+        discord.remove_event_config_role("stream.online", "r2").await.unwrap();
+        let config = discord.get_event_config_by_name("stream.online").await.unwrap().unwrap();
+        assert_eq!(config.ping_roles, Some(vec!["r1".to_string()]));
+
+        discord.set_live_role("guild", "live").await.unwrap();
+        assert_eq!(discord.get_live_role("guild").await.unwrap().unwrap().role_id, "live");
+    }
+
+    #[tokio::test]
+    async fn test_list_columns_decode_from_json() {
+        let (_db, repos) = migrated().await;
+        let now = Utc::now();
+        repos.alert_rules.create_rule(&AlertRule {
+            rule_id: Uuid::new_v4(),
+            name: "auth".into(),
+            kinds: vec![AlertKind::PlatformDisconnect, AlertKind::CredentialFailure],
+            min_severity: AlertSeverity::Info,
+            route: AlertRoute::Chat,
+            target: None,
+            dedupe_secs: 60,
+            quiet_hours: None,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }).await.unwrap();
+        let rule = repos.alert_rules.get_rule_by_name("AUTH").await.unwrap().unwrap();
+        assert_eq!(rule.kinds, vec![AlertKind::PlatformDisconnect, AlertKind::CredentialFailure]);
+        // The seeded desktop rule handles every kind
+        let desktop = repos.alert_rules.get_rule_by_name("desktop").await.unwrap().unwrap();
+        assert!(desktop.kinds.is_empty());
+        assert!(!repos.ai_providers.list_providers().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_workspaces_on_one_platform_keep_their_own_commands() {
+        use maowbot_common::models::command::Command;
+        use maowbot_common::models::credential::CredentialType;
+        use maowbot_common::models::platform::{Platform, PlatformCredential};
+        use maowbot_common::models::workspace::{Workspace, DEFAULT_WORKSPACE_ID};
+
+        let (_db, repos) = migrated().await;
+        let now = Utc::now();
+        let brand = Workspace {
+            workspace_id: Uuid::new_v4(),
+            name: "brand".into(),
+            display_name: None,
+            created_at: now,
+        };
+        repos.workspaces.create_workspace(&brand).await.unwrap();
+        repos.workspaces.assign_channel(brand.workspace_id, "twitch-irc", "#Brand").await.unwrap();
+
+        let command = |name: &str, response: &str| Command {
+            command_id: Uuid::new_v4(),
+            platform: "twitch-irc".into(),
+            command_name: name.into(),
+            min_role: "viewer".into(),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            cooldown_seconds: 0,
+            cooldown_warnonce: false,
+            respond_with_credential: None,
+            stream_online_only: false,
+            stream_offline_only: false,
+            active_credential_id: None,
+            user_cooldown_seconds: 0,
+            role_cooldowns: Default::default(),
+            cooldown_bypass_mods: false,
+            reply_privately: false,
+            reply_to_message: false,
+            response: Some(response.into()),
+        };
+        let scoped = repos.for_workspace(brand.workspace_id);
+        scoped.commands.create_command(&command("socials", "brand links")).await.unwrap();
+        repos.commands.create_command(&command("socials", "maow links")).await.unwrap();
+        scoped.commands.create_command(&command("merch", "brand store")).await.unwrap();
+
+        let mine = scoped.commands.get_command_by_name("twitch-irc", "socials").await.unwrap().unwrap();
+        assert_eq!(mine.response.as_deref(), Some("brand links"));
+        let theirs = repos.commands.get_command_by_name("twitch-irc", "socials").await.unwrap().unwrap();
+        assert_eq!(theirs.response.as_deref(), Some("maow links"));
+        assert!(repos.commands.get_command_by_name("twitch-irc", "merch").await.unwrap().is_none());
+        assert_eq!(repos.commands.list_commands("twitch-irc").await.unwrap().len(), 1);
+        assert_eq!(scoped.commands.list_commands("twitch-irc").await.unwrap().len(), 2);
+
+        // Credentials block the delete instead of going with the workspace
+        let owner = user(&repos, "brand").await;
+        scoped.credentials.store_credentials(&PlatformCredential {
+            credential_id: Uuid::new_v4(),
+            platform: Platform::TwitchIRC,
+            platform_id: Some("1".into()),
+            credential_type: CredentialType::OAuth2,
+            user_id: owner,
+            user_name: "brand".into(),
+            primary_token: "token".into(),
+            refresh_token: None,
+            additional_data: None,
+            expires_at: None,
+            created_at: now,
+            updated_at: now,
+            is_bot: false,
+            is_teammate: false,
+            is_broadcaster: true,
+        }).await.unwrap();
+        assert!(matches!(
+            repos.workspaces.delete_workspace(brand.workspace_id).await,
+            Err(crate::Error::ValidationError(_))
+        ));
+        assert_eq!(repos.credentials.get_all_credentials().await.unwrap().len(), 1);
+
+        scoped.credentials.delete_credentials(&Platform::TwitchIRC, owner).await.unwrap();
+        repos.workspaces.delete_workspace(brand.workspace_id).await.unwrap();
+        assert!(repos.workspaces.list_channels().await.unwrap().is_empty());
+        assert_eq!(
+            repos.commands.list_commands("twitch-irc").await.unwrap()[0].response.as_deref(),
+            Some("maow links")
+        );
+        assert!(repos.workspaces.get_workspace(DEFAULT_WORKSPACE_ID).await.unwrap().is_some());
+    }
+}
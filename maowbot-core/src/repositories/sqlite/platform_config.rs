@@ -0,0 +1,116 @@
+// File: maowbot-core/src/repositories/sqlite/platform_config.rs
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::platform::PlatformConfig;
+pub use maowbot_common::traits::repository_traits::PlatformConfigRepository;
+
+const CONFIG_COLUMNS: &str = "platform_config_id, platform, client_id, client_secret, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct SqlitePlatformConfigRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqlitePlatformConfigRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_config(r: &SqliteRow) -> Result<PlatformConfig, Error> {
+    Ok(PlatformConfig {
+        platform_config_id: r.try_get("platform_config_id")?,
+        platform: r.try_get("platform")?,
+        client_id: r.try_get("client_id")?,
+        client_secret: r.try_get("client_secret")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl PlatformConfigRepository for SqlitePlatformConfigRepository {
+    async fn upsert_platform_config(
+        &self,
+        platform: &str,
+        client_id: Option<String>,
+        client_secret: Option<String>,
+    ) -> Result<(), Error> {
+        let now = Utc::now();
+        if let Some(pc) = self.get_by_platform(platform).await? {
+            sqlx::query(
+                "UPDATE platform_config SET client_id = ?1, client_secret = ?2, updated_at = ?3 WHERE platform_config_id = ?4"
+            )
+                .bind(client_id)
+                .bind(client_secret)
+                .bind(now)
+                .bind(pc.platform_config_id)
+                .execute(&self.pool)
+                .await?;
+        } else {
+            sqlx::query(&format!(
+                "INSERT INTO platform_config ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6)", CONFIG_COLUMNS
+            ))
+                .bind(Uuid::new_v4())
+                .bind(platform)
+                .bind(client_id)
+                .bind(client_secret)
+                .bind(now)
+                .bind(now)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_platform_config(&self, platform_config_id: Uuid) -> Result<Option<PlatformConfig>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM platform_config WHERE platform_config_id = ?1", CONFIG_COLUMNS
+        ))
+            .bind(platform_config_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_config).transpose()
+    }
+
+    async fn list_platform_configs(&self, maybe_platform: Option<&str>) -> Result<Vec<PlatformConfig>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM platform_config WHERE (?1 IS NULL OR LOWER(platform) = LOWER(?1)) ORDER BY created_at DESC",
+            CONFIG_COLUMNS
+        ))
+            .bind(maybe_platform)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_config).collect()
+    }
+
+    async fn delete_platform_config(&self, platform_config_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM platform_config WHERE platform_config_id = ?1")
+            .bind(platform_config_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_by_platform(&self, platform: &str) -> Result<Option<PlatformConfig>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM platform_config WHERE LOWER(platform) = LOWER(?1) LIMIT 1", CONFIG_COLUMNS
+        ))
+            .bind(platform)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_config).transpose()
+    }
+
+    async fn count_for_platform(&self, platform: &str) -> Result<i64, Error> {
+        let row = sqlx::query("SELECT COUNT(*) AS count FROM platform_config WHERE LOWER(platform) = LOWER(?1)")
+            .bind(platform)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.try_get("count")?)
+    }
+}
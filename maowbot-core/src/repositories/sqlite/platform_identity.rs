@@ -0,0 +1,154 @@
+// File: maowbot-core/src/repositories/sqlite/platform_identity.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::platform::{Platform, PlatformIdentity};
+pub use maowbot_common::traits::repository_traits::PlatformIdentityRepo;
+
+const IDENTITY_COLUMNS: &str = "platform_identity_id, user_id, platform, platform_user_id, \
+    platform_username, platform_display_name, platform_roles, platform_data, created_at, last_updated";
+
+#[derive(Clone)]
+pub struct SqlitePlatformIdentityRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqlitePlatformIdentityRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+/// Roles and platform data are stored as JSON text.
+fn row_to_identity(r: &SqliteRow) -> Result<PlatformIdentity, Error> {
+    let roles: String = r.try_get("platform_roles")?;
+    let data: String = r.try_get("platform_data")?;
+    Ok(PlatformIdentity {
+        platform_identity_id: r.try_get("platform_identity_id")?,
+        user_id: r.try_get("user_id")?,
+        platform: Platform::from(r.try_get::<String, _>("platform")?),
+        platform_user_id: r.try_get("platform_user_id")?,
+        platform_username: r.try_get("platform_username")?,
+        platform_display_name: r.try_get("platform_display_name")?,
+        platform_roles: serde_json::from_str(&roles)?,
+        platform_data: serde_json::from_str(&data)?,
+        created_at: r.try_get("created_at")?,
+        last_updated: r.try_get("last_updated")?,
+    })
+}
+
+#[async_trait]
+impl PlatformIdentityRepo for SqlitePlatformIdentityRepository {
+    async fn create(&self, identity: &PlatformIdentity) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO platform_identities ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            IDENTITY_COLUMNS
+        ))
+            .bind(identity.platform_identity_id)
+            .bind(identity.user_id)
+            .bind(identity.platform.to_string())
+            .bind(&identity.platform_user_id)
+            .bind(&identity.platform_username)
+            .bind(&identity.platform_display_name)
+            .bind(serde_json::to_string(&identity.platform_roles)?)
+            .bind(identity.platform_data.to_string())
+            .bind(identity.created_at)
+            .bind(identity.last_updated)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<PlatformIdentity>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM platform_identities WHERE platform_identity_id = ?1", IDENTITY_COLUMNS
+        ))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_identity).transpose()
+    }
+
+    async fn update(&self, identity: &PlatformIdentity) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE platform_identities
+            SET user_id               = ?1,
+                platform              = ?2,
+                platform_user_id      = ?3,
+                platform_username     = ?4,
+                platform_display_name = ?5,
+                platform_roles        = ?6,
+                platform_data         = ?7,
+                last_updated          = ?8
+            WHERE platform_identity_id = ?9
+            "#,
+        )
+            .bind(identity.user_id)
+            .bind(identity.platform.to_string())
+            .bind(&identity.platform_user_id)
+            .bind(&identity.platform_username)
+            .bind(&identity.platform_display_name)
+            .bind(serde_json::to_string(&identity.platform_roles)?)
+            .bind(identity.platform_data.to_string())
+            .bind(identity.last_updated)
+            .bind(identity.platform_identity_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM platform_identities WHERE platform_identity_id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_by_platform(&self, platform: Platform, platform_user_id: &str)
+                             -> Result<Option<PlatformIdentity>, Error>
+    {
+        let row_opt = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM platform_identities
+            WHERE platform = ?1
+              AND (LOWER(platform_user_id) = LOWER(?2) OR LOWER(platform_username) = LOWER(?2))
+            "#,
+            IDENTITY_COLUMNS
+        ))
+            .bind(platform.to_string())
+            .bind(platform_user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_identity).transpose()
+    }
+
+    async fn get_all_for_user(&self, user_id: Uuid) -> Result<Vec<PlatformIdentity>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM platform_identities WHERE user_id = ?1", IDENTITY_COLUMNS
+        ))
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_identity).collect()
+    }
+
+    async fn get_by_user_and_platform(
+        &self,
+        user_id: Uuid,
+        platform: &Platform,
+    ) -> Result<Option<PlatformIdentity>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM platform_identities WHERE user_id = ?1 AND platform = ?2 LIMIT 1",
+            IDENTITY_COLUMNS
+        ))
+            .bind(user_id)
+            .bind(platform.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_identity).transpose()
+    }
+}
@@ -0,0 +1,104 @@
+// File: maowbot-core/src/repositories/sqlite/protection.rs
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::ProtectionIncidentRepository;
+use maowbot_common::models::protection::ProtectionIncident;
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteProtectionIncidentRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteProtectionIncidentRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn incident_from_row(row: &SqliteRow) -> Result<ProtectionIncident, Error> {
+    let trigger: String = row.try_get("trigger")?;
+    let actions: JsonValue = row.try_get("actions")?;
+    let messages: JsonValue = row.try_get("messages")?;
+    Ok(ProtectionIncident {
+        incident_id: row.try_get("incident_id")?,
+        channel: row.try_get("channel")?,
+        trigger: trigger.parse()?,
+        details: row.try_get("details")?,
+        actions: serde_json::from_value(actions)?,
+        messages: serde_json::from_value(messages)?,
+        started_at: row.try_get("started_at")?,
+        ended_at: row.try_get("ended_at")?,
+        ended_by: row.try_get("ended_by")?,
+    })
+}
+
+#[async_trait]
+impl ProtectionIncidentRepository for SqliteProtectionIncidentRepository {
+    async fn create_incident(&self, incident: &ProtectionIncident) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO protection_incidents
+                (incident_id, channel, trigger, details, actions, messages, started_at, ended_at, ended_by)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#
+        )
+            .bind(incident.incident_id)
+            .bind(&incident.channel)
+            .bind(incident.trigger.to_string())
+            .bind(&incident.details)
+            .bind(serde_json::to_value(&incident.actions)?)
+            .bind(serde_json::to_value(&incident.messages)?)
+            .bind(incident.started_at)
+            .bind(incident.ended_at)
+            .bind(&incident.ended_by)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_incident(&self, incident: &ProtectionIncident) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE protection_incidents
+            SET actions = ?2, messages = ?3, ended_at = ?4, ended_by = ?5
+            WHERE incident_id = ?1
+            "#
+        )
+            .bind(incident.incident_id)
+            .bind(serde_json::to_value(&incident.actions)?)
+            .bind(serde_json::to_value(&incident.messages)?)
+            .bind(incident.ended_at)
+            .bind(&incident.ended_by)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_incident(&self, incident_id: Uuid) -> Result<Option<ProtectionIncident>, Error> {
+        let row = sqlx::query("SELECT * FROM protection_incidents WHERE incident_id = ?1")
+            .bind(incident_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(incident_from_row).transpose()
+    }
+
+    async fn list_incidents(&self, limit: i64) -> Result<Vec<ProtectionIncident>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT incident_id, channel, trigger, details, actions,
+                   '[]' AS messages, started_at, ended_at, ended_by
+            FROM protection_incidents
+            ORDER BY started_at DESC
+            LIMIT ?1
+            "#
+        )
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(incident_from_row).collect()
+    }
+}
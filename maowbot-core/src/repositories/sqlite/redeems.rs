@@ -0,0 +1,180 @@
+// File: maowbot-core/src/repositories/sqlite/redeems.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::redeem::Redeem;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::RedeemRepository;
+
+const REDEEM_COLUMNS: &str = "redeem_id, platform, reward_id, reward_name, cost, is_active, dynamic_pricing, \
+    active_offline, is_managed, plugin_name, command_name, created_at, updated_at, active_credential_id, \
+    is_input_required, redeem_prompt_text, requires_approval";
+
+/// Channel point redeems for one workspace (the default workspace unless `for_workspace` is used).
+#[derive(Clone)]
+pub struct SqliteRedeemRepository {
+    pool: Pool<Sqlite>,
+    workspace_id: Uuid,
+}
+
+impl SqliteRedeemRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool, workspace_id: DEFAULT_WORKSPACE_ID }
+    }
+
+    /// A repository over the same pool scoped to `workspace_id`.
+    pub fn for_workspace(&self, workspace_id: Uuid) -> Self {
+        Self { pool: self.pool.clone(), workspace_id }
+    }
+}
+
+fn row_to_redeem(r: &SqliteRow) -> Result<Redeem, Error> {
+    Ok(Redeem {
+        redeem_id: r.try_get("redeem_id")?,
+        platform: r.try_get("platform")?,
+        reward_id: r.try_get("reward_id")?,
+        reward_name: r.try_get("reward_name")?,
+        cost: r.try_get("cost")?,
+        is_active: r.try_get("is_active")?,
+        dynamic_pricing: r.try_get("dynamic_pricing")?,
+        active_offline: r.try_get("active_offline")?,
+        is_managed: r.try_get("is_managed")?,
+        plugin_name: r.try_get("plugin_name")?,
+        command_name: r.try_get("command_name")?,
+        created_at: r.try_get("created_at")?,
+        updated_at: r.try_get("updated_at")?,
+        active_credential_id: r.try_get("active_credential_id")?,
+        is_input_required: r.try_get("is_input_required")?,
+        redeem_prompt_text: r.try_get("redeem_prompt_text")?,
+        requires_approval: r.try_get("requires_approval")?,
+    })
+}
+
+#[async_trait]
+impl RedeemRepository for SqliteRedeemRepository {
+    async fn create_redeem(&self, rd: &Redeem) -> Result<(), Error> {
+        sqlx::query(&format!(
+            "INSERT INTO redeems ({}, workspace_id) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            REDEEM_COLUMNS
+        ))
+            .bind(rd.redeem_id)
+            .bind(&rd.platform)
+            .bind(&rd.reward_id)
+            .bind(&rd.reward_name)
+            .bind(rd.cost)
+            .bind(rd.is_active)
+            .bind(rd.dynamic_pricing)
+            .bind(rd.active_offline)
+            .bind(rd.is_managed)
+            .bind(&rd.plugin_name)
+            .bind(&rd.command_name)
+            .bind(rd.created_at)
+            .bind(rd.updated_at)
+            .bind(rd.active_credential_id)
+            .bind(rd.is_input_required)
+            .bind(&rd.redeem_prompt_text)
+            .bind(rd.requires_approval)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_redeem_by_id(&self, redeem_id: Uuid) -> Result<Option<Redeem>, Error> {
+        let row_opt = sqlx::query(&format!(
+            "SELECT {} FROM redeems WHERE redeem_id = ?1 AND workspace_id = ?2", REDEEM_COLUMNS
+        ))
+            .bind(redeem_id)
+            .bind(self.workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_redeem).transpose()
+    }
+
+    async fn get_redeem_by_reward_id(&self, platform: &str, reward_id: &str) -> Result<Option<Redeem>, Error> {
+        let row_opt = sqlx::query(&format!(
+            r#"
+            SELECT {} FROM redeems
+            WHERE LOWER(platform) = LOWER(?1)
+              AND LOWER(reward_id) = LOWER(?2)
+              AND workspace_id = ?3
+            "#,
+            REDEEM_COLUMNS
+        ))
+            .bind(platform)
+            .bind(reward_id)
+            .bind(self.workspace_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_redeem).transpose()
+    }
+
+    async fn list_redeems(&self, platform: &str) -> Result<Vec<Redeem>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM redeems WHERE LOWER(platform) = LOWER(?1) AND workspace_id = ?2 ORDER BY reward_name ASC",
+            REDEEM_COLUMNS
+        ))
+            .bind(platform)
+            .bind(self.workspace_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_redeem).collect()
+    }
+
+    async fn update_redeem(&self, rd: &Redeem) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE redeems
+            SET platform = ?1,
+                reward_id = ?2,
+                reward_name = ?3,
+                cost = ?4,
+                is_active = ?5,
+                dynamic_pricing = ?6,
+                active_offline = ?7,
+                is_managed = ?8,
+                plugin_name = ?9,
+                command_name = ?10,
+                updated_at = ?11,
+                active_credential_id = ?12,
+                is_input_required = ?13,
+                redeem_prompt_text = ?14,
+                requires_approval = ?15
+            WHERE redeem_id = ?16
+              AND workspace_id = ?17
+            "#,
+        )
+            .bind(&rd.platform)
+            .bind(&rd.reward_id)
+            .bind(&rd.reward_name)
+            .bind(rd.cost)
+            .bind(rd.is_active)
+            .bind(rd.dynamic_pricing)
+            .bind(rd.active_offline)
+            .bind(rd.is_managed)
+            .bind(&rd.plugin_name)
+            .bind(&rd.command_name)
+            .bind(rd.updated_at)
+            .bind(rd.active_credential_id)
+            .bind(rd.is_input_required)
+            .bind(&rd.redeem_prompt_text)
+            .bind(rd.requires_approval)
+            .bind(rd.redeem_id)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_redeem(&self, redeem_id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM redeems WHERE redeem_id = ?1 AND workspace_id = ?2")
+            .bind(redeem_id)
+            .bind(self.workspace_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
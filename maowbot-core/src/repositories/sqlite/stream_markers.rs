@@ -0,0 +1,149 @@
+// File: maowbot-core/src/repositories/sqlite/stream_markers.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+pub use maowbot_common::traits::repository_traits::StreamMarkerRepository;
+use maowbot_common::models::stream_marker::{StreamMarker, VodChapters};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteStreamMarkerRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteStreamMarkerRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn marker_from_row(row: &SqliteRow) -> Result<StreamMarker, Error> {
+    let source: String = row.try_get("source")?;
+    Ok(StreamMarker {
+        marker_id: row.try_get("marker_id")?,
+        broadcaster_id: row.try_get("broadcaster_id")?,
+        stream_id: row.try_get("stream_id")?,
+        twitch_marker_id: row.try_get("twitch_marker_id")?,
+        position_seconds: row.try_get("position_seconds")?,
+        description: row.try_get("description")?,
+        source: source.parse()?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn chapters_from_row(row: &SqliteRow) -> Result<VodChapters, Error> {
+    Ok(VodChapters {
+        stream_id: row.try_get("stream_id")?,
+        broadcaster_id: row.try_get("broadcaster_id")?,
+        video_id: row.try_get("video_id")?,
+        video_url: row.try_get("video_url")?,
+        title: row.try_get("title")?,
+        chapters: row.try_get("chapters")?,
+        marker_count: row.try_get("marker_count")?,
+        exported_at: row.try_get("exported_at")?,
+    })
+}
+
+#[async_trait]
+impl StreamMarkerRepository for SqliteStreamMarkerRepository {
+    async fn insert_marker(&self, marker: &StreamMarker) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO stream_markers (
+                marker_id, broadcaster_id, stream_id, twitch_marker_id,
+                position_seconds, description, source, created_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#
+        )
+            .bind(marker.marker_id)
+            .bind(&marker.broadcaster_id)
+            .bind(&marker.stream_id)
+            .bind(&marker.twitch_marker_id)
+            .bind(marker.position_seconds)
+            .bind(&marker.description)
+            .bind(marker.source.to_string())
+            .bind(marker.created_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_markers(&self, stream_id: &str) -> Result<Vec<StreamMarker>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT marker_id, broadcaster_id, stream_id, twitch_marker_id,
+                   position_seconds, description, source, created_at
+            FROM stream_markers
+            WHERE stream_id = ?1
+            ORDER BY position_seconds, created_at
+            "#
+        )
+            .bind(stream_id)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(marker_from_row).collect()
+    }
+
+    async fn save_vod_chapters(&self, chapters: &VodChapters) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO vod_chapters (
+                stream_id, broadcaster_id, video_id, video_url,
+                title, chapters, marker_count, exported_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (stream_id) DO UPDATE SET
+                video_id = EXCLUDED.video_id,
+                video_url = EXCLUDED.video_url,
+                title = EXCLUDED.title,
+                chapters = EXCLUDED.chapters,
+                marker_count = EXCLUDED.marker_count,
+                exported_at = EXCLUDED.exported_at
+            "#
+        )
+            .bind(&chapters.stream_id)
+            .bind(&chapters.broadcaster_id)
+            .bind(&chapters.video_id)
+            .bind(&chapters.video_url)
+            .bind(&chapters.title)
+            .bind(&chapters.chapters)
+            .bind(chapters.marker_count)
+            .bind(chapters.exported_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_vod_chapters(&self, id: &str) -> Result<Option<VodChapters>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT stream_id, broadcaster_id, video_id, video_url,
+                   title, chapters, marker_count, exported_at
+            FROM vod_chapters
+            WHERE stream_id = ?1 OR video_id = ?1
+            LIMIT 1
+            "#
+        )
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(chapters_from_row).transpose()
+    }
+
+    async fn list_vod_chapters(&self, limit: i64) -> Result<Vec<VodChapters>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT stream_id, broadcaster_id, video_id, video_url,
+                   title, chapters, marker_count, exported_at
+            FROM vod_chapters
+            ORDER BY exported_at DESC
+            LIMIT ?1
+            "#
+        )
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(chapters_from_row).collect()
+    }
+}
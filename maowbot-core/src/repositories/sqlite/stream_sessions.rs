@@ -0,0 +1,236 @@
+// File: maowbot-core/src/repositories/sqlite/stream_sessions.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::StreamSessionRepository;
+use maowbot_common::models::stream_session::{SessionChatter, SessionStats, StreamSession};
+use crate::Error;
+
+const SESSION_COLUMNS: &str = "session_id, platform, channel, broadcaster_id, stream_id, title, category, \
+    started_at, ended_at, chat_messages, unique_chatters, follows, subs, gifted_subs, bits, raids, \
+    redemptions, hype_moments, peak_viewers, updated_at";
+
+#[derive(Clone)]
+pub struct SqliteStreamSessionRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteStreamSessionRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn session_from_row(row: &SqliteRow) -> Result<StreamSession, Error> {
+    Ok(StreamSession {
+        session_id: row.try_get("session_id")?,
+        platform: row.try_get("platform")?,
+        channel: row.try_get("channel")?,
+        broadcaster_id: row.try_get("broadcaster_id")?,
+        stream_id: row.try_get("stream_id")?,
+        title: row.try_get("title")?,
+        category: row.try_get("category")?,
+        started_at: row.try_get("started_at")?,
+        ended_at: row.try_get("ended_at")?,
+        stats: SessionStats {
+            chat_messages: row.try_get("chat_messages")?,
+            unique_chatters: row.try_get("unique_chatters")?,
+            follows: row.try_get("follows")?,
+            subs: row.try_get("subs")?,
+            gifted_subs: row.try_get("gifted_subs")?,
+            bits: row.try_get("bits")?,
+            raids: row.try_get("raids")?,
+            redemptions: row.try_get("redemptions")?,
+            hype_moments: row.try_get("hype_moments")?,
+            peak_viewers: row.try_get("peak_viewers")?,
+        },
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl StreamSessionRepository for SqliteStreamSessionRepository {
+    async fn create_session(&self, session: &StreamSession) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO stream_sessions (
+                session_id, platform, channel, broadcaster_id, stream_id,
+                title, category, started_at, ended_at, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#
+        )
+            .bind(session.session_id)
+            .bind(&session.platform)
+            .bind(&session.channel)
+            .bind(&session.broadcaster_id)
+            .bind(&session.stream_id)
+            .bind(&session.title)
+            .bind(&session.category)
+            .bind(session.started_at)
+            .bind(session.ended_at)
+            .bind(session.updated_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_session(&self, session_id: Uuid) -> Result<Option<StreamSession>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {SESSION_COLUMNS} FROM stream_sessions WHERE session_id = ?1"
+        ))
+            .bind(session_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(session_from_row).transpose()
+    }
+
+    async fn get_session_by_stream_id(&self, stream_id: &str) -> Result<Option<StreamSession>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {SESSION_COLUMNS} FROM stream_sessions WHERE stream_id = ?1"
+        ))
+            .bind(stream_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(session_from_row).transpose()
+    }
+
+    async fn get_open_session(&self, channel: &str) -> Result<Option<StreamSession>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {SESSION_COLUMNS} FROM stream_sessions \
+             WHERE channel = ?1 AND ended_at IS NULL \
+             ORDER BY started_at DESC LIMIT 1"
+        ))
+            .bind(channel)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(session_from_row).transpose()
+    }
+
+    async fn get_latest_session(&self) -> Result<Option<StreamSession>, Error> {
+        let row = sqlx::query(&format!(
+            "SELECT {SESSION_COLUMNS} FROM stream_sessions ORDER BY started_at DESC LIMIT 1"
+        ))
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(session_from_row).transpose()
+    }
+
+    async fn list_sessions(&self, limit: i64) -> Result<Vec<StreamSession>, Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT {SESSION_COLUMNS} FROM stream_sessions ORDER BY started_at DESC LIMIT ?1"
+        ))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(session_from_row).collect()
+    }
+
+    async fn update_details(&self, session_id: Uuid, title: &str, category: &str) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE stream_sessions SET title = ?2, category = ?3, updated_at = CURRENT_TIMESTAMP WHERE session_id = ?1"
+        )
+            .bind(session_id)
+            .bind(title)
+            .bind(category)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn save_stats(&self, session_id: Uuid, stats: &SessionStats) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE stream_sessions
+            SET chat_messages = ?2, unique_chatters = ?3, follows = ?4, subs = ?5,
+                gifted_subs = ?6, bits = ?7, raids = ?8, redemptions = ?9,
+                hype_moments = ?10, peak_viewers = ?11, updated_at = CURRENT_TIMESTAMP
+            WHERE session_id = ?1
+            "#
+        )
+            .bind(session_id)
+            .bind(stats.chat_messages)
+            .bind(stats.unique_chatters)
+            .bind(stats.follows)
+            .bind(stats.subs)
+            .bind(stats.gifted_subs)
+            .bind(stats.bits)
+            .bind(stats.raids)
+            .bind(stats.redemptions)
+            .bind(stats.hype_moments)
+            .bind(stats.peak_viewers)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn close_session(&self, session_id: Uuid, ended_at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE stream_sessions SET ended_at = ?2, updated_at = CURRENT_TIMESTAMP \
+             WHERE session_id = ?1 AND ended_at IS NULL"
+        )
+            .bind(session_id)
+            .bind(ended_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn add_chatter_messages(&self, session_id: Uuid, chatters: &[(String, String, i64)]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for (twitch_user_id, display_name, messages) in chatters {
+            sqlx::query(
+                r#"
+                INSERT INTO stream_session_chatters (session_id, twitch_user_id, display_name, messages)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT (session_id, twitch_user_id) DO UPDATE
+                SET messages = stream_session_chatters.messages + EXCLUDED.messages,
+                    display_name = EXCLUDED.display_name
+                "#
+            )
+                .bind(session_id)
+                .bind(twitch_user_id)
+                .bind(display_name)
+                .bind(messages)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn count_chatters(&self, session_id: Uuid) -> Result<i64, Error> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM stream_session_chatters WHERE session_id = ?1"
+        )
+            .bind(session_id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    async fn top_chatters(&self, session_id: Uuid, limit: i64) -> Result<Vec<SessionChatter>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT twitch_user_id, display_name, messages
+            FROM stream_session_chatters
+            WHERE session_id = ?1
+            ORDER BY messages DESC, display_name
+            LIMIT ?2
+            "#
+        )
+            .bind(session_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(|row| -> Result<SessionChatter, Error> {
+            Ok(SessionChatter {
+                twitch_user_id: row.try_get("twitch_user_id")?,
+                display_name: row.try_get("display_name")?,
+                messages: row.try_get("messages")?,
+            })
+        }).collect()
+    }
+}
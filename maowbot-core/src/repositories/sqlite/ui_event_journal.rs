@@ -0,0 +1,80 @@
+// File: maowbot-core/src/repositories/sqlite/ui_event_journal.rs
+
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use sqlx::{Pool, QueryBuilder, Sqlite, Row};
+
+use crate::services::ui_events::{UiEvent, UiEventJournal, UiEventKind};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteUiEventJournal {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteUiEventJournal {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UiEventJournal for SqliteUiEventJournal {
+    async fn last_seq(&self) -> Result<Option<i64>, Error> {
+        Ok(sqlx::query_scalar("SELECT MAX(seq) FROM bot_events")
+            .fetch_one(&self.pool)
+            .await?)
+    }
+
+    async fn insert_events(&self, batch: &[UiEvent]) -> Result<(), Error> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "INSERT INTO bot_events (seq, kind, event_type, event_timestamp, data) "
+        );
+        builder.push_values(batch, |mut b, e| {
+            b.push_bind(e.seq)
+                .push_bind(e.kind.as_str())
+                .push_bind(e.event_type.as_str())
+                .push_bind(e.timestamp)
+                .push_bind(Value::Object(e.data.clone()));
+        });
+        builder.push(" ON CONFLICT (seq) DO NOTHING");
+        builder.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn events_between(&self, after: i64, before: i64, limit: i64) -> Result<Vec<UiEvent>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT seq, kind, event_type, event_timestamp, data
+            FROM bot_events
+            WHERE seq > ?1 AND seq < ?2
+            ORDER BY seq DESC
+            LIMIT ?3
+            "#,
+        )
+            .bind(after)
+            .bind(before)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().filter_map(row_to_event).collect())
+    }
+}
+
+fn row_to_event(row: &sqlx::sqlite::SqliteRow) -> Option<UiEvent> {
+    let kind: Option<String> = row.try_get("kind").ok()?;
+    let data: Option<Value> = row.try_get("data").ok()?;
+    Some(UiEvent {
+        seq: row.try_get("seq").ok()?,
+        kind: UiEventKind::parse(kind.as_deref()?)?,
+        event_type: row.try_get("event_type").ok()?,
+        timestamp: row.try_get("event_timestamp").ok()?,
+        data: match data {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        },
+    })
+}
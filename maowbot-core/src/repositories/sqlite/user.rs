@@ -0,0 +1,189 @@
+// File: maowbot-core/src/repositories/sqlite/user.rs
+
+use async_trait::async_trait;
+use sqlx::{sqlite::SqliteRow, Pool, QueryBuilder, Row, Sqlite};
+use uuid::Uuid;
+use maowbot_common::error::Error;
+use maowbot_common::models::user::{User, UserListQuery, UserSort};
+pub use maowbot_common::traits::repository_traits::UserRepo;
+
+#[derive(Clone)]
+pub struct SqliteUserRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_user(r: &SqliteRow) -> Result<User, Error> {
+    Ok(User {
+        user_id: r.try_get("user_id")?,
+        global_username: r.try_get("global_username")?,
+        created_at: r.try_get("created_at")?,
+        last_seen: r.try_get("last_seen")?,
+        is_active: r.try_get("is_active")?,
+    })
+}
+
+fn push_user_filters(builder: &mut QueryBuilder<'_, Sqlite>, query: &UserListQuery) {
+    builder.push(" WHERE 1 = 1");
+    if query.active_only {
+        builder.push(" AND u.is_active");
+    }
+    if !query.platforms.is_empty() {
+        builder.push(" AND EXISTS (SELECT 1 FROM platform_identities pi WHERE pi.user_id = u.user_id AND LOWER(pi.platform) IN (");
+        let mut list = builder.separated(", ");
+        for platform in &query.platforms {
+            list.push_bind(platform.to_lowercase());
+        }
+        builder.push("))");
+    }
+    if !query.roles.is_empty() {
+        // platform_roles is a JSON array of strings
+        builder.push(" AND EXISTS (SELECT 1 FROM platform_identities pi, json_each(pi.platform_roles) r WHERE pi.user_id = u.user_id AND r.value IN (");
+        let mut list = builder.separated(", ");
+        for role in &query.roles {
+            list.push_bind(role.clone());
+        }
+        builder.push("))");
+    }
+    if let Some(prefix) = query.name_prefix.as_deref().filter(|p| !p.is_empty()) {
+        let escaped = prefix.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        builder.push(" AND LOWER(u.global_username) LIKE ").push_bind(format!("{}%", escaped)).push(" ESCAPE '\\'");
+    }
+}
+
+#[async_trait]
+impl UserRepo for SqliteUserRepository {
+    async fn create(&self, user: &User) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO users (user_id, created_at, last_seen, is_active, global_username)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+            .bind(user.user_id)
+            .bind(user.created_at)
+            .bind(user.last_seen)
+            .bind(user.is_active)
+            .bind(&user.global_username)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<User>, Error> {
+        let row_opt = sqlx::query(
+            "SELECT user_id, global_username, created_at, last_seen, is_active FROM users WHERE user_id = ?1"
+        )
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_user).transpose()
+    }
+
+    async fn get_by_global_username(&self, name: &str) -> Result<Option<User>, Error> {
+        let row_opt = sqlx::query(
+            r#"
+            SELECT user_id, global_username, created_at, last_seen, is_active
+            FROM users
+            WHERE LOWER(global_username) = LOWER(?1)
+            "#,
+        )
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        row_opt.as_ref().map(row_to_user).transpose()
+    }
+
+    async fn update(&self, user: &User) -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE users SET global_username = ?1, last_seen = ?2, is_active = ?3 WHERE user_id = ?4"
+        )
+            .bind(&user.global_username)
+            .bind(user.last_seen)
+            .bind(user.is_active)
+            .bind(user.user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM users WHERE user_id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_all(&self) -> Result<Vec<User>, Error> {
+        let rows = sqlx::query(
+            "SELECT user_id, global_username, created_at, last_seen, is_active FROM users ORDER BY created_at ASC"
+        )
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(row_to_user).collect()
+    }
+
+    async fn list_page(&self, query: &UserListQuery) -> Result<(Vec<User>, i64), Error> {
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM users u");
+        push_user_filters(&mut count, query);
+        let total: i64 = count.build().fetch_one(&self.pool).await?.try_get(0)?;
+
+        let mut select = QueryBuilder::new(
+            "SELECT u.user_id, u.global_username, u.created_at, u.last_seen, u.is_active FROM users u"
+        );
+        push_user_filters(&mut select, query);
+        let column = match query.sort {
+            UserSort::CreatedAt => "u.created_at",
+            UserSort::LastSeen => "u.last_seen",
+            UserSort::Username => "LOWER(u.global_username)",
+        };
+        let direction = if query.descending { "DESC" } else { "ASC" };
+        // user_id breaks ties so pages don't overlap or skip rows
+        select.push(format!(" ORDER BY {} {} NULLS LAST, u.user_id {}", column, direction, direction))
+            .push(" LIMIT ").push_bind(query.limit.max(1))
+            .push(" OFFSET ").push_bind(query.offset.max(0));
+        let rows = select.build().fetch_all(&self.pool).await?;
+        let users = rows.iter().map(row_to_user).collect::<Result<Vec<_>, _>>()?;
+        Ok((users, total))
+    }
+
+    async fn merge_users(&self, primary_user_id: Uuid, duplicate_user_ids: Vec<Uuid>) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for dup_id in duplicate_user_ids {
+            for table in MERGED_TABLES {
+                sqlx::query(&format!("UPDATE {} SET user_id = ?1 WHERE user_id = ?2", table))
+                    .bind(primary_user_id)
+                    .bind(dup_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            // The primary user's analysis is kept
+            sqlx::query("DELETE FROM user_analysis WHERE user_id = ?1")
+                .bind(dup_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM users WHERE user_id = ?1")
+                .bind(dup_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Tables whose rows follow a user into a merge.
+const MERGED_TABLES: [&str; 6] = [
+    "platform_identities",
+    "command_usage",
+    "redeem_usage",
+    "user_audit_log",
+    "user_notes",
+    "user_moderation_actions",
+];
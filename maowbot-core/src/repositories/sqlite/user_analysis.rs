@@ -0,0 +1,182 @@
+// File: maowbot-core/src/repositories/sqlite/user_analysis.rs
+
+use sqlx::{types::Json, Pool, Sqlite, Row};
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+use maowbot_common::models::UserAnalysis;
+pub use maowbot_common::traits::repository_traits::UserAnalysisRepository;
+use crate::Error;
+use crate::repositories::postgres::user_analysis::deduplicate_ai_notes;
+
+
+#[derive(Clone)]
+pub struct SqliteUserAnalysisRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteUserAnalysisRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+
+#[async_trait]
+impl UserAnalysisRepository for SqliteUserAnalysisRepository {
+    async fn create_analysis(&self, analysis: &UserAnalysis) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_analysis (
+                user_analysis_id,
+                user_id,
+                spam_score,
+                intelligibility_score,
+                quality_score,
+                horni_score,
+                ai_notes,
+                moderator_notes,
+                bot_score,
+                bot_signals,
+                bot_scored_at,
+                created_at,
+                updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            "#,
+        )
+            .bind(analysis.user_analysis_id)
+            .bind(analysis.user_id)
+            .bind(analysis.spam_score)
+            .bind(analysis.intelligibility_score)
+            .bind(analysis.quality_score)
+            .bind(analysis.horni_score)
+            .bind(&analysis.ai_notes)
+            .bind(&analysis.moderator_notes)
+            .bind(analysis.bot_score)
+            .bind(Json(&analysis.bot_signals))
+            .bind(analysis.bot_scored_at)
+            .bind(analysis.created_at)
+            .bind(analysis.updated_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_analysis(&self, user_id: Uuid) -> Result<Option<UserAnalysis>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT user_analysis_id,
+                   user_id,
+                   spam_score,
+                   intelligibility_score,
+                   quality_score,
+                   horni_score,
+                   ai_notes,
+                   moderator_notes,
+                   bot_score,
+                   bot_signals,
+                   bot_scored_at,
+                   created_at,
+                   updated_at
+            FROM user_analysis
+            WHERE user_id = ?1
+            "#,
+        )
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        if let Some(r) = row {
+            let ua = UserAnalysis {
+                user_analysis_id: r.try_get("user_analysis_id")?,
+                user_id: r.try_get("user_id")?,
+                spam_score: r.try_get("spam_score")?,
+                intelligibility_score: r.try_get("intelligibility_score")?,
+                quality_score: r.try_get("quality_score")?,
+                horni_score: r.try_get("horni_score")?,
+                ai_notes: r.try_get("ai_notes")?,
+                moderator_notes: r.try_get("moderator_notes")?,
+                bot_score: r.try_get("bot_score")?,
+                bot_signals: r.try_get::<Json<Vec<String>>, _>("bot_signals")?.0,
+                bot_scored_at: r.try_get("bot_scored_at")?,
+                created_at: r.try_get("created_at")?,
+                updated_at: r.try_get("updated_at")?,
+            };
+            Ok(Some(ua))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update_analysis(&self, analysis: &UserAnalysis) -> Result<(), Error> {
+        // Repeated monthly summary lines are dropped, as in Postgres
+        let sanitized_notes = analysis.ai_notes.as_ref().map(|notes| deduplicate_ai_notes(notes));
+
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            UPDATE user_analysis
+            SET spam_score = ?1,
+                intelligibility_score = ?2,
+                quality_score = ?3,
+                horni_score = ?4,
+                ai_notes = ?5,
+                moderator_notes = ?6,
+                bot_score = ?7,
+                bot_signals = ?8,
+                bot_scored_at = ?9,
+                updated_at = ?10
+            WHERE user_analysis_id = ?11
+            "#,
+        )
+            .bind(analysis.spam_score)
+            .bind(analysis.intelligibility_score)
+            .bind(analysis.quality_score)
+            .bind(analysis.horni_score)
+            .bind(&sanitized_notes)
+            .bind(&analysis.moderator_notes)
+            .bind(analysis.bot_score)
+            .bind(Json(&analysis.bot_signals))
+            .bind(analysis.bot_scored_at)
+            .bind(now)
+            .bind(analysis.user_analysis_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_history(&self, year_month: &str, scores: &UserAnalysis) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_analysis_history (
+                user_analysis_history_id,
+                user_id,
+                year_month,
+                spam_score,
+                intelligibility_score,
+                quality_score,
+                horni_score,
+                ai_notes,
+                created_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+        )
+            .bind(Uuid::new_v4())
+            .bind(scores.user_id)
+            .bind(year_month)
+            .bind(scores.spam_score)
+            .bind(scores.intelligibility_score)
+            .bind(scores.quality_score)
+            .bind(scores.horni_score)
+            .bind(&scores.ai_notes)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+// File: maowbot-core/src/repositories/sqlite/user_notes.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Pool, Sqlite, Row};
+use uuid::Uuid;
+pub use maowbot_common::traits::repository_traits::UserNotesRepository;
+use maowbot_common::models::user_notes::{ModerationAction, UserNote};
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SqliteUserNotesRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteUserNotesRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+fn action_from_row(row: &SqliteRow) -> Result<ModerationAction, Error> {
+    let action_type: String = row.try_get("action_type")?;
+    Ok(ModerationAction {
+        action_id: row.try_get("action_id")?,
+        user_id: row.try_get("user_id")?,
+        platform: row.try_get("platform")?,
+        channel: row.try_get("channel")?,
+        action_type: action_type.parse()?,
+        reason: row.try_get("reason")?,
+        duration_seconds: row.try_get("duration_seconds")?,
+        moderator: row.try_get("moderator")?,
+        category: row.try_get("category")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+#[async_trait]
+impl UserNotesRepository for SqliteUserNotesRepository {
+    async fn add_note(&self, note: &UserNote) -> Result<(), Error> {
+        if note.note_text.trim().is_empty() {
+            return Err(Error::ValidationError("Note text cannot be empty".into()));
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO user_notes (note_id, user_id, author, note_text, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#
+        )
+            .bind(note.note_id)
+            .bind(note.user_id)
+            .bind(&note.author)
+            .bind(&note.note_text)
+            .bind(note.created_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_notes(&self, user_id: Uuid, limit: i64) -> Result<Vec<UserNote>, Error> {
+        let notes = sqlx::query_as::<_, UserNote>(
+            r#"
+            SELECT note_id, user_id, author, note_text, created_at
+            FROM user_notes
+            WHERE user_id = ?1
+            ORDER BY created_at DESC
+            LIMIT ?2
+            "#
+        )
+            .bind(user_id)
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(notes)
+    }
+
+    async fn delete_note(&self, note_id: Uuid) -> Result<bool, Error> {
+        let res = sqlx::query("DELETE FROM user_notes WHERE note_id = ?1")
+            .bind(note_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn record_action(&self, action: &ModerationAction) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_moderation_actions (
+                action_id, user_id, platform, channel, action_type,
+                reason, duration_seconds, moderator, category, created_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            "#
+        )
+            .bind(action.action_id)
+            .bind(action.user_id)
+            .bind(&action.platform)
+            .bind(&action.channel)
+            .bind(action.action_type.to_string())
+            .bind(&action.reason)
+            .bind(action.duration_seconds)
+            .bind(&action.moderator)
+            .bind(&action.category)
+            .bind(action.created_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_actions(&self, user_id: Uuid, limit: i64) -> Result<Vec<ModerationAction>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT action_id, user_id, platform, channel, action_type,
+                   reason, duration_seconds, moderator, category, created_at
+            FROM user_moderation_actions
+            WHERE user_id = ?1
+            ORDER BY created_at DESC
+            LIMIT ?2
+            "#
+        )
+            .bind(user_id)
+            .bind(limit.max(1))
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(action_from_row).collect()
+    }
+
+    async fn list_offenses(&self, user_id: Uuid, since: Option<DateTime<Utc>>) -> Result<Vec<ModerationAction>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.action_id, a.user_id, a.platform, a.channel, a.action_type,
+                   a.reason, a.duration_seconds, a.moderator, a.category, a.created_at
+            FROM user_moderation_actions a
+            WHERE a.user_id = ?1
+              AND a.category <> ''
+              AND a.action_type IN ('warning', 'timeout', 'ban')
+              AND (?2 IS NULL OR a.created_at > ?2)
+              AND NOT EXISTS (
+                  SELECT 1 FROM user_moderation_actions p
+                  WHERE p.user_id = a.user_id
+                    AND p.category = a.category
+                    AND p.action_type = 'pardon'
+                    AND p.created_at >= a.created_at
+              )
+            ORDER BY a.created_at DESC
+            "#
+        )
+            .bind(user_id)
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(action_from_row).collect()
+    }
+}
@@ -0,0 +1,192 @@
+// File: maowbot-core/src/services/ai_safety.rs
+//
+// Post-processing for AI answers before they reach chat or TTS. The !askai,
+// !askmao and search redeems pass the model's text through here: personal
+// details (emails, phone numbers, card and social security numbers, IP
+// addresses) are redacted, words on `ai.safety.profanity_words` are masked,
+// and the text is cut into Twitch-sized chunks marked "(1/3) … " so viewers
+// know to `!continue`. Channels on `ai.safety.family_friendly_channels` also
+// mask a built-in list of swears, and an answer that needed more than a few
+// of those is withheld rather than sent with holes in it.
+
+use std::sync::Arc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::services::outbound_chain::{platform_max_length, profanity_regex, split_text};
+use crate::settings::SettingsRegistry;
+
+/// Masked by family-friendly channels on top of `ai.safety.profanity_words`.
+const FAMILY_FRIENDLY_WORDS: &str = "fuck,fucking,fucked,fucker,shit,shitty,bullshit,bitch,bitches,cunt,\
+    asshole,bastard,dick,cock,pussy,slut,whore,damn,goddamn,crap,piss,pissed";
+/// Family-friendly answers that needed more masks than this are withheld.
+const FAMILY_FRIENDLY_MAX_MASKED: usize = 3;
+const REDACTED: &str = "[redacted]";
+const CONTINUED: &str = " …";
+
+static PII_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        // email
+        r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+        // US social security number
+        r"\b\d{3}-\d{2}-\d{4}\b",
+        // card numbers, grouped or not
+        r"\b\d{4}[ -]?\d{4}[ -]?\d{4}[ -]?\d{1,4}\b",
+        // phone numbers with separators, optionally with a country code
+        r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)|\b\d{3})[\s.-]\d{3}[\s.-]\d{4}\b",
+        // IPv4 addresses
+        r"\b(?:\d{1,3}\.){3}\d{1,3}\b",
+    ]
+    .iter()
+    .map(|p| Regex::new(p).unwrap())
+    .collect()
+});
+static URL: Lazy<Regex> = Lazy::new(|| Regex::new(r"https?://\S+").unwrap());
+
+/// An AI answer ready to send.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapedResponse {
+    /// Chat lines in order; the first is sent now, the rest wait for `!continue`
+    pub chunks: Vec<String>,
+    /// One line for TTS: no chunk markers or links
+    pub tts: String,
+    /// Personal details replaced with "[redacted]"
+    pub redacted: usize,
+    /// Words masked with asterisks
+    pub masked: usize,
+    /// True when the answer was replaced with a refusal
+    pub withheld: bool,
+}
+
+/// Replaces every personal detail in `text`; returns the new text and the count.
+pub fn redact_pii(text: &str) -> (String, usize) {
+    let mut count = 0;
+    let mut out = text.to_string();
+    for re in PII_PATTERNS.iter() {
+        count += re.find_iter(&out).count();
+        out = re.replace_all(&out, REDACTED).into_owned();
+    }
+    (out, count)
+}
+
+fn mask_words(re: &Regex, text: &str) -> (String, usize) {
+    let count = re.find_iter(text).count();
+    let masked = re.replace_all(text, |caps: &regex::Captures| "*".repeat(caps[0].chars().count()));
+    (masked.into_owned(), count)
+}
+
+/// Cuts `text` into at most `max_chunks` lines of `max_len` characters,
+/// marking each with "(n/total)" and every one but the last with " …". An
+/// answer too long for `max_chunks` is cut off at the end of the last chunk.
+pub fn chunk_for_chat(text: &str, max_len: usize, max_chunks: usize) -> Vec<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= max_len {
+        return if text.is_empty() { vec![] } else { vec![text] };
+    }
+    let max_chunks = max_chunks.max(1);
+    // Room for "(nn/nn) " in front and the continuation mark behind
+    let marker = format!("({}/{}) ", max_chunks, max_chunks).chars().count() + CONTINUED.chars().count();
+    let mut parts = split_text(&text, max_len.saturating_sub(marker).max(1));
+    if parts.len() > max_chunks {
+        parts.truncate(max_chunks);
+        if let Some(last) = parts.last_mut() {
+            last.push('…');
+        }
+    }
+    let total = parts.len();
+    parts.into_iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let more = if i + 1 < total { CONTINUED } else { "" };
+            format!("({}/{}) {}{}", i + 1, total, part, more)
+        })
+        .collect()
+}
+
+pub struct AiResponseShaper {
+    settings: Arc<SettingsRegistry>,
+}
+
+impl AiResponseShaper {
+    pub fn new(settings: Arc<SettingsRegistry>) -> Self {
+        Self { settings }
+    }
+
+    /// Whether `channel` is on `ai.safety.family_friendly_channels` (`*` for all).
+    pub fn is_family_friendly(&self, channel: &str) -> bool {
+        let list = self.settings.get("ai.safety.family_friendly_channels").unwrap_or_default();
+        let channel = channel.trim_start_matches('#');
+        list.split(',')
+            .map(|c| c.trim().trim_start_matches('#'))
+            .any(|c| c == "*" || (!c.is_empty() && c.eq_ignore_ascii_case(channel)))
+    }
+
+    /// Filters `text` for `channel` on `platform` and cuts it to size.
+    pub fn shape(&self, platform: &str, channel: &str, text: &str) -> ShapedResponse {
+        let family_friendly = self.is_family_friendly(channel);
+        let mut shaped = ShapedResponse::default();
+
+        let mut text = text.to_string();
+        if self.settings.get_bool("ai.safety.redact_pii").unwrap_or(true) {
+            let (redacted, count) = redact_pii(&text);
+            text = redacted;
+            shaped.redacted = count;
+        }
+
+        let mut words = self.settings.get("ai.safety.profanity_words").unwrap_or_default();
+        if family_friendly {
+            words = format!("{},{}", words, FAMILY_FRIENDLY_WORDS);
+        }
+        if let Some(re) = profanity_regex(&words) {
+            let (masked, count) = mask_words(&re, &text);
+            text = masked;
+            shaped.masked = count;
+        }
+        if family_friendly && shaped.masked > FAMILY_FRIENDLY_MAX_MASKED {
+            shaped.withheld = true;
+            text = self.settings.get("ai.safety.withheld_message")
+                .filter(|m| !m.trim().is_empty())
+                .unwrap_or_else(|| "I'd better not answer that one here.".to_string());
+        }
+
+        let max_len = platform_max_length(platform).unwrap_or(500);
+        let max_chunks = self.settings.get_u64("ai.safety.max_chunks").unwrap_or(4) as usize;
+        shaped.chunks = chunk_for_chat(&text, max_len, max_chunks);
+
+        let tts_max = self.settings.get_u64("ai.safety.tts_max_chars").unwrap_or(300) as usize;
+        let tts = URL.replace_all(&text, "").split_whitespace().collect::<Vec<_>>().join(" ");
+        shaped.tts = if tts.chars().count() > tts_max {
+            let mut cut: String = tts.chars().take(tts_max).collect();
+            if let Some(end) = cut.rfind(char::is_whitespace) {
+                cut.truncate(end);
+            }
+            cut
+        } else {
+            tts
+        };
+        shaped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_personal_details_but_not_ordinary_numbers() {
+        let (text, count) = redact_pii("Mail me@example.com or call (555) 123-4567 from 10.0.0.1, it's 2024 and 42 cats");
+        assert_eq!(text, "Mail [redacted] or call [redacted] from [redacted], it's 2024 and 42 cats");
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_chunks_fit_the_limit_with_markers() {
+        let answer = "word ".repeat(300);
+        let chunks = chunk_for_chat(&answer, 500, 3);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 500));
+        assert!(chunks[0].starts_with("(1/3) ") && chunks[0].ends_with(" …"));
+        assert!(chunks[2].starts_with("(3/3) ") && chunks[2].ends_with('…'));
+        assert_eq!(chunk_for_chat("short answer", 500, 3), vec!["short answer"]);
+    }
+}
@@ -0,0 +1,506 @@
+// File: maowbot-core/src/services/alerting.rs
+//
+// Alerts for operational problems: platform connections that keep dropping,
+// credentials that can't be renewed, bursts of failed pipeline executions and
+// low disk space, plus repeat offenders the moderation ladder timed out or
+// banned. Connection, credential and moderation alerts come in as events; the
+// pipeline and disk checks run every minute. Each alert goes through the alert
+// rules in order: a rule that matches its kind and severity sends it to a
+// Discord DM, a Twitch chat or the desktop GUI, unless the rule sent the same
+// alert within its dedupe window or it's quiet hours and the alert isn't
+// critical.
+// Every alert is published as `BotEvent::OpsAlert`.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::alert_rule::{AlertKind, AlertRoute, AlertRule, AlertSeverity, OpsAlert, QuietHours};
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::event_pipeline_traits::PipelineExecutionLogRepository;
+use maowbot_common::traits::repository_traits::{AlertRuleRepository, CredentialsRepository};
+
+use crate::disk::free_disk_bytes;
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::manager::PlatformManager;
+use crate::services::message_sender::MessageSender;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(60);
+/// Alerts kept for `recent_alerts`.
+const RECENT_ALERTS: usize = 100;
+
+/// What's needed to add a rule.
+#[derive(Debug, Clone)]
+pub struct NewAlertRule {
+    pub name: String,
+    pub kinds: Vec<AlertKind>,
+    pub min_severity: AlertSeverity,
+    pub route: AlertRoute,
+    pub target: Option<String>,
+    pub dedupe_secs: i32,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+/// Changes to a rule; `None` leaves a field as it is. An empty target clears
+/// it, and so do empty quiet hours.
+#[derive(Debug, Clone, Default)]
+pub struct AlertRuleEdit {
+    pub kinds: Option<Vec<AlertKind>>,
+    pub min_severity: Option<AlertSeverity>,
+    pub route: Option<AlertRoute>,
+    pub target: Option<String>,
+    pub dedupe_secs: Option<i32>,
+    pub quiet_hours: Option<Option<QuietHours>>,
+    pub enabled: Option<bool>,
+}
+
+/// What a rule did with an alert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    Sent,
+    /// The rule sent the same alert within its dedupe window
+    Deduped,
+    /// Held back by the rule's quiet hours
+    Quiet,
+    Failed(String),
+}
+
+/// An alert and what each matching rule did with it.
+#[derive(Debug, Clone)]
+pub struct RaisedAlert {
+    pub alert: OpsAlert,
+    /// (rule name, outcome) for every rule the alert matched
+    pub deliveries: Vec<(String, DeliveryOutcome)>,
+}
+
+#[derive(Default)]
+struct AlertingState {
+    /// When each rule last sent each alert, by (rule, "kind:key")
+    last_sent: HashMap<(Uuid, String), DateTime<Utc>>,
+    /// Connection keys that raised an alert and haven't reconnected since
+    disconnected: HashSet<String>,
+    recent: VecDeque<RaisedAlert>,
+}
+
+impl AlertingState {
+    fn deduped(&self, rule: &AlertRule, alert_key: &str, now: DateTime<Utc>) -> bool {
+        rule.dedupe_secs > 0
+            && self.last_sent.get(&(rule.rule_id, alert_key.to_string()))
+                .is_some_and(|at| now - *at < Duration::seconds(rule.dedupe_secs as i64))
+    }
+}
+
+/// The severity for `failures` failed executions against a threshold of
+/// `threshold`; None below it or when the check is off.
+pub fn pipeline_severity(failures: i64, threshold: u64) -> Option<AlertSeverity> {
+    if threshold == 0 || failures < threshold as i64 {
+        None
+    } else if failures >= threshold as i64 * 5 {
+        Some(AlertSeverity::Critical)
+    } else {
+        Some(AlertSeverity::Warning)
+    }
+}
+
+/// The severity for `free_mb` free against a minimum of `min_free_mb`; None
+/// when there's enough or the check is off.
+pub fn disk_severity(free_mb: u64, min_free_mb: u64) -> Option<AlertSeverity> {
+    if min_free_mb == 0 || free_mb >= min_free_mb {
+        None
+    } else if free_mb < min_free_mb / 4 {
+        Some(AlertSeverity::Critical)
+    } else {
+        Some(AlertSeverity::Warning)
+    }
+}
+
+fn clean_target(target: Option<&str>) -> Option<String> {
+    target.map(str::trim).filter(|t| !t.is_empty()).map(str::to_string)
+}
+
+fn alert_text(alert: &OpsAlert) -> String {
+    if alert.message.is_empty() {
+        format!("[{}] {}", alert.severity.to_string().to_uppercase(), alert.title)
+    } else {
+        format!("[{}] {}: {}", alert.severity.to_string().to_uppercase(), alert.title, alert.message)
+    }
+}
+
+pub struct AlertingService {
+    rules_repo: Arc<dyn AlertRuleRepository>,
+    pipeline_repo: Arc<dyn PipelineExecutionLogRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    state: Mutex<AlertingState>,
+}
+
+impl AlertingService {
+    pub fn new(
+        rules_repo: Arc<dyn AlertRuleRepository>,
+        pipeline_repo: Arc<dyn PipelineExecutionLogRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            rules_repo,
+            pipeline_repo,
+            credentials_repo,
+            platform_manager,
+            event_bus,
+            settings,
+            state: Mutex::new(AlertingState::default()),
+        }
+    }
+
+    /// Watches connection, credential and moderation events, and checks
+    /// pipelines and disk space every minute.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Alerting] event loop stopped");
+        });
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+                if let Err(e) = service.check_pipelines().await {
+                    debug!("[Alerting] pipeline check failed: {:?}", e);
+                }
+                if let Err(e) = service.check_disk().await {
+                    debug!("[Alerting] disk check failed: {:?}", e);
+                }
+            }
+            debug!("[Alerting] check loop stopped");
+        });
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        match event {
+            BotEvent::PlatformConnectionChanged { platform, account_name, state, attempt, error, .. } => {
+                let key = format!("{}:{}", platform, account_name);
+                let threshold = self.settings.get_u64("alerting.reconnect_attempts").unwrap_or(3) as u32;
+                let severity = match state.as_str() {
+                    "failed" => AlertSeverity::Critical,
+                    "reconnecting" if attempt >= threshold.max(1) => AlertSeverity::Warning,
+                    "connected" => {
+                        if self.state.lock().disconnected.remove(&key) {
+                            self.raise(AlertKind::PlatformDisconnect, AlertSeverity::Info, &key,
+                                format!("{} ({}) reconnected", platform, account_name), String::new()).await;
+                        }
+                        return;
+                    }
+                    _ => return,
+                };
+                self.state.lock().disconnected.insert(key.clone());
+                let title = if state == "failed" {
+                    format!("{} ({}) gave up reconnecting", platform, account_name)
+                } else {
+                    format!("{} ({}) is reconnecting, attempt {}", platform, account_name, attempt)
+                };
+                self.raise(AlertKind::PlatformDisconnect, severity, &key, title, error.unwrap_or_default()).await;
+            }
+            BotEvent::CredentialRefreshFailed { platform, user_name, expires_at, error, .. } => {
+                // Tokens that are already gone, or about to be, need attention now
+                let severity = match expires_at {
+                    Some(at) if at - Utc::now() > Duration::hours(1) => AlertSeverity::Warning,
+                    _ => AlertSeverity::Critical,
+                };
+                let title = match expires_at {
+                    Some(at) => format!("Could not renew the {} login for {} (expires {})",
+                        platform, user_name, at.format("%Y-%m-%d %H:%M UTC")),
+                    None => format!("Could not renew the {} login for {}", platform, user_name),
+                };
+                self.raise(AlertKind::CredentialFailure, severity, &format!("{}:{}", platform, user_name), title, error).await;
+            }
+            BotEvent::ModerationEscalated { user, category, rule, offense, action, .. } => {
+                let title = format!("{} got {} for {} (offense {} in {})", user, action, rule, offense, category);
+                let message = format!("Undo with `automod pardon {} {} --lift`", user, category);
+                self.raise(AlertKind::Moderation, AlertSeverity::Warning,
+                    &format!("{}:{}:{}", user, category, offense), title, message).await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn check_pipelines(&self) -> Result<(), Error> {
+        let threshold = self.settings.get_u64("alerting.pipeline_failures").unwrap_or(10);
+        if threshold == 0 {
+            return Ok(());
+        }
+        let minutes = self.settings.get_u64("alerting.pipeline_window_minutes").unwrap_or(15).max(1);
+        let since = Utc::now() - Duration::minutes(minutes as i64);
+        let failures = self.pipeline_repo.count_failed_executions_since(since).await?;
+        if let Some(severity) = pipeline_severity(failures, threshold) {
+            self.raise(AlertKind::PipelineFailures, severity, "pipelines",
+                format!("{} pipeline executions failed in the last {} minutes", failures, minutes),
+                "See `pipeline history` for the errors".to_string()).await;
+        }
+        Ok(())
+    }
+
+    async fn check_disk(&self) -> Result<(), Error> {
+        let min_free_mb = self.settings.get_u64("alerting.disk_min_free_mb").unwrap_or(2048);
+        if min_free_mb == 0 {
+            return Ok(());
+        }
+        let path = self.settings.get("alerting.disk_path")
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        let free_mb = free_disk_bytes(Path::new(&path)).await.map_err(Error::Internal)? / (1024 * 1024);
+        if let Some(severity) = disk_severity(free_mb, min_free_mb) {
+            self.raise(AlertKind::LowDisk, severity, &path,
+                format!("Only {} MB free on the disk holding {}", free_mb, path),
+                format!("Alerting below {} MB", min_free_mb)).await;
+        }
+        Ok(())
+    }
+
+    fn local_time(&self, now: DateTime<Utc>) -> NaiveTime {
+        let zone = self.settings.get("alerting.timezone").unwrap_or_default();
+        match zone.trim().parse::<chrono_tz::Tz>() {
+            Ok(tz) => now.with_timezone(&tz).time(),
+            Err(_) => now.time(),
+        }
+    }
+
+    async fn raise(&self, kind: AlertKind, severity: AlertSeverity, key: &str, title: String, message: String) {
+        if !self.settings.get_bool("alerting.enabled").unwrap_or(true) {
+            return;
+        }
+        let alert = OpsAlert { kind, severity, key: key.to_string(), title, message, raised_at: Utc::now() };
+        info!("[Alerting] {} {}: {}", alert.severity, alert.kind, alert.title);
+        self.dispatch(alert, false).await;
+    }
+
+    /// Runs `alert` through the rules. `force` skips dedupe and quiet hours.
+    async fn dispatch(&self, alert: OpsAlert, force: bool) -> RaisedAlert {
+        let rules = match self.rules_repo.list_rules().await {
+            Ok(rules) => rules,
+            Err(e) => {
+                warn!("[Alerting] could not load alert rules: {:?}", e);
+                Vec::new()
+            }
+        };
+        let alert_key = format!("{}:{}", alert.kind, alert.key);
+        let local_time = self.local_time(alert.raised_at);
+
+        let mut deliveries = Vec::new();
+        let mut desktop = false;
+        for rule in rules.iter().filter(|rule| rule.matches(&alert)) {
+            let held = if force {
+                None
+            } else if rule.is_quiet(&alert, local_time) {
+                Some(DeliveryOutcome::Quiet)
+            } else if self.state.lock().deduped(rule, &alert_key, alert.raised_at) {
+                Some(DeliveryOutcome::Deduped)
+            } else {
+                None
+            };
+            let outcome = match held {
+                Some(outcome) => outcome,
+                None => match self.deliver(rule, &alert).await {
+                    Ok(()) => {
+                        self.state.lock().last_sent.insert((rule.rule_id, alert_key.clone()), alert.raised_at);
+                        desktop |= rule.route == AlertRoute::Desktop;
+                        DeliveryOutcome::Sent
+                    }
+                    Err(e) => {
+                        warn!("[Alerting] rule '{}' could not send: {:?}", rule.name, e);
+                        DeliveryOutcome::Failed(e.to_string())
+                    }
+                },
+            };
+            deliveries.push((rule.name.clone(), outcome));
+        }
+
+        self.event_bus.publish(BotEvent::OpsAlert {
+            kind: alert.kind,
+            severity: alert.severity,
+            key: alert.key.clone(),
+            title: alert.title.clone(),
+            message: alert.message.clone(),
+            desktop,
+            timestamp: alert.raised_at,
+        }).await;
+
+        let raised = RaisedAlert { alert, deliveries };
+        let mut state = self.state.lock();
+        state.recent.push_front(raised.clone());
+        state.recent.truncate(RECENT_ALERTS);
+        raised
+    }
+
+    async fn deliver(&self, rule: &AlertRule, alert: &OpsAlert) -> Result<(), Error> {
+        let text = alert_text(alert);
+        match rule.route {
+            AlertRoute::DiscordDm => {
+                let user_id = rule.target.as_deref()
+                    .ok_or_else(|| Error::ValidationError("No Discord user id to DM".into()))?;
+                let account = match self.settings.get("alerting.discord_account").filter(|a| !a.trim().is_empty()) {
+                    Some(account) => account.trim().to_string(),
+                    None => {
+                        let creds = self.credentials_repo.list_credentials_for_platform(&Platform::Discord).await?;
+                        creds.into_iter().find(|c| c.is_bot)
+                            .map(|c| c.user_name)
+                            .ok_or_else(|| Error::Platform("No Discord bot credential".into()))?
+                    }
+                };
+                let discord = self.platform_manager.get_discord_instance(&account).await?;
+                discord.send_direct_message(user_id, &text).await
+            }
+            AlertRoute::Chat => {
+                let channel = match &rule.target {
+                    Some(channel) => channel.clone(),
+                    None => self.credentials_repo.get_broadcaster_credential(&Platform::Twitch).await?
+                        .map(|c| c.user_name)
+                        .ok_or_else(|| Error::Platform("No Twitch broadcaster credential".into()))?,
+                };
+                let channel = if channel.starts_with('#') { channel } else { format!("#{}", channel) };
+                MessageSender::new(self.credentials_repo.clone(), self.platform_manager.clone())
+                    .send_twitch_message(&channel, &text, None, Uuid::nil())
+                    .await
+            }
+            // The GUI shows alerts published with `desktop` set
+            AlertRoute::Desktop => Ok(()),
+        }
+    }
+
+    /// Raises a made-up alert that skips dedupe and quiet hours, to check
+    /// where rules send it.
+    pub async fn test_alert(&self, kind: AlertKind, severity: AlertSeverity) -> RaisedAlert {
+        let alert = OpsAlert {
+            kind,
+            severity,
+            key: "test".to_string(),
+            title: format!("Test {} alert", kind),
+            message: "Sent with `alerting test`".to_string(),
+            raised_at: Utc::now(),
+        };
+        self.dispatch(alert, true).await
+    }
+
+    /// Newest first.
+    pub fn recent_alerts(&self, limit: usize) -> Vec<RaisedAlert> {
+        self.state.lock().recent.iter().take(limit).cloned().collect()
+    }
+
+    pub async fn add_rule(&self, new: NewAlertRule) -> Result<AlertRule, Error> {
+        let name = new.name.trim().to_string();
+        if name.is_empty() {
+            return Err(Error::Parse("A rule needs a name".into()));
+        }
+        if self.rules_repo.get_rule_by_name(&name).await?.is_some() {
+            return Err(Error::Parse(format!("An alert rule named '{}' already exists", name)));
+        }
+        let now = Utc::now();
+        let rule = AlertRule {
+            rule_id: Uuid::new_v4(),
+            name,
+            kinds: new.kinds,
+            min_severity: new.min_severity,
+            route: new.route,
+            target: clean_target(new.target.as_deref()),
+            dedupe_secs: new.dedupe_secs,
+            quiet_hours: new.quiet_hours,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+        rule.validate()?;
+        self.rules_repo.create_rule(&rule).await?;
+        info!("[Alerting] added rule '{}' ({})", rule.name, rule.route);
+        Ok(rule)
+    }
+
+    pub async fn edit_rule(&self, name: &str, edit: AlertRuleEdit) -> Result<AlertRule, Error> {
+        let mut rule = self.get_rule(name).await?;
+        if let Some(kinds) = edit.kinds {
+            rule.kinds = kinds;
+        }
+        rule.min_severity = edit.min_severity.unwrap_or(rule.min_severity);
+        rule.route = edit.route.unwrap_or(rule.route);
+        if let Some(target) = edit.target {
+            rule.target = clean_target(Some(&target));
+        }
+        rule.dedupe_secs = edit.dedupe_secs.unwrap_or(rule.dedupe_secs);
+        if let Some(quiet_hours) = edit.quiet_hours {
+            rule.quiet_hours = quiet_hours;
+        }
+        rule.enabled = edit.enabled.unwrap_or(rule.enabled);
+        rule.validate()?;
+        rule.updated_at = Utc::now();
+        self.rules_repo.update_rule(&rule).await?;
+        Ok(rule)
+    }
+
+    pub async fn delete_rule(&self, name: &str) -> Result<(), Error> {
+        let rule = self.get_rule(name).await?;
+        self.rules_repo.delete_rule(rule.rule_id).await?;
+        self.state.lock().last_sent.retain(|(rule_id, _), _| *rule_id != rule.rule_id);
+        info!("[Alerting] deleted rule '{}'", rule.name);
+        Ok(())
+    }
+
+    pub async fn get_rule(&self, name: &str) -> Result<AlertRule, Error> {
+        self.rules_repo.get_rule_by_name(name.trim()).await?
+            .ok_or_else(|| Error::NotFound(format!("No alert rule named '{}'", name.trim())))
+    }
+
+    pub async fn list_rules(&self) -> Result<Vec<AlertRule>, Error> {
+        self.rules_repo.list_rules().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severities_scale_with_how_bad_it_is() {
+        assert_eq!(pipeline_severity(3, 10), None);
+        assert_eq!(pipeline_severity(10, 10), Some(AlertSeverity::Warning));
+        assert_eq!(pipeline_severity(50, 10), Some(AlertSeverity::Critical));
+        assert_eq!(pipeline_severity(500, 0), None);
+
+        assert_eq!(disk_severity(4096, 2048), None);
+        assert_eq!(disk_severity(1500, 2048), Some(AlertSeverity::Warning));
+        assert_eq!(disk_severity(100, 2048), Some(AlertSeverity::Critical));
+        assert_eq!(disk_severity(0, 0), None);
+    }
+}
@@ -0,0 +1,167 @@
+// File: maowbot-core/src/services/chat_identity.rs
+//
+// Which of several accounts on a platform speaks in a given channel, so a
+// user bot and a broadcaster bot (or one bot per brand) can share a setup.
+// Assignments live in `chat.identities` as
+// {"twitch-irc": {"*": "mainbot", "somechannel": "brandbot"}}, by account
+// name, where "*" is the platform default. A command's own
+// `respond_with_credential` still wins over both; without any assignment the
+// usual bot-then-broadcaster pick applies.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::warn;
+
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+pub const IDENTITIES_SETTING: &str = "chat.identities";
+/// The channel key of a platform's default identity.
+pub const ANY_CHANNEL: &str = "*";
+
+fn normalize_channel(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Account names by platform, then channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdentityMap {
+    platforms: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl IdentityMap {
+    pub fn parse(json: &str) -> Result<IdentityMap, Error> {
+        let raw: BTreeMap<String, BTreeMap<String, String>> = serde_json::from_str(json)
+            .map_err(|e| Error::Parse(format!("{}: {}", IDENTITIES_SETTING, e)))?;
+        let mut map = IdentityMap::default();
+        for (platform, channels) in raw {
+            for (channel, account) in channels {
+                map.set(&platform, &channel, Some(&account));
+            }
+        }
+        Ok(map)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.platforms).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// The channel's own assignment, else the platform default.
+    pub fn account(&self, platform: &str, channel: &str) -> Option<&str> {
+        let channels = self.platforms.get(&platform.to_lowercase())?;
+        channels.get(&normalize_channel(channel))
+            .or_else(|| channels.get(ANY_CHANNEL))
+            .map(String::as_str)
+    }
+
+    /// Assigns `account` to the channel, or clears the assignment with None.
+    pub fn set(&mut self, platform: &str, channel: &str, account: Option<&str>) {
+        let platform = platform.to_lowercase();
+        let channel = normalize_channel(channel);
+        match account.map(str::trim).filter(|a| !a.is_empty()) {
+            Some(account) => {
+                self.platforms.entry(platform).or_default().insert(channel, account.to_string());
+            }
+            None => {
+                if let Some(channels) = self.platforms.get_mut(&platform) {
+                    channels.remove(&channel);
+                    if channels.is_empty() {
+                        self.platforms.remove(&platform);
+                    }
+                }
+            }
+        }
+    }
+
+    /// (platform, channel, account), sorted.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.platforms.iter().flat_map(|(platform, channels)| {
+            channels.iter().map(move |(channel, account)| (platform.as_str(), channel.as_str(), account.as_str()))
+        })
+    }
+}
+
+/// Resolves and changes per-channel identities.
+pub struct ChatIdentityRouter {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    settings: Arc<SettingsRegistry>,
+}
+
+impl ChatIdentityRouter {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self { credentials_repo, settings }
+    }
+
+    /// The current assignments; an invalid setting is logged and ignored.
+    pub fn identities(&self) -> IdentityMap {
+        let Some(raw) = self.settings.get(IDENTITIES_SETTING) else {
+            return IdentityMap::default();
+        };
+        IdentityMap::parse(&raw).unwrap_or_else(|e| {
+            warn!("Ignoring {}: {}", IDENTITIES_SETTING, e);
+            IdentityMap::default()
+        })
+    }
+
+    /// The platform's credential with this login.
+    pub async fn find_account(&self, platform: &Platform, account: &str) -> Result<Option<PlatformCredential>, Error> {
+        let creds = self.credentials_repo.list_credentials_for_platform(platform).await?;
+        Ok(creds.into_iter().find(|c| c.user_name.eq_ignore_ascii_case(account)))
+    }
+
+    /// The credential assigned to speak in `channel`, if any. An assignment
+    /// to an account that has since been removed is logged and skipped.
+    pub async fn credential_for(&self, platform: &Platform, channel: &str) -> Result<Option<PlatformCredential>, Error> {
+        let identities = self.identities();
+        let Some(account) = identities.account(&platform.to_string(), channel) else {
+            return Ok(None);
+        };
+        let found = self.find_account(platform, account).await?;
+        if found.is_none() {
+            warn!("{} assigns '{}' to {} {}, but there is no such {} account", IDENTITIES_SETTING, account, platform, channel, platform);
+        }
+        Ok(found)
+    }
+
+    /// Assigns `account` to speak in `channel` ("*" for every channel without
+    /// its own), or clears the assignment with None.
+    pub async fn assign(&self, platform: &Platform, channel: &str, account: Option<&str>) -> Result<Option<PlatformCredential>, Error> {
+        let credential = match account {
+            Some(account) => Some(self.find_account(platform, account).await?.ok_or_else(|| Error::NotFound(format!(
+                "No {} account named '{}'; add it with `account add {} {}`", platform, account, platform, account
+            )))?),
+            None => None,
+        };
+        let mut identities = self.identities();
+        identities.set(&platform.to_string(), channel, credential.as_ref().map(|c| c.user_name.as_str()));
+        self.settings.set(IDENTITIES_SETTING, &identities.to_json()).await?;
+        Ok(credential)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_assignment_beats_platform_default() {
+        let mut map = IdentityMap::parse(r##"{"twitch-irc": {"*": "mainbot", "#BrandChannel": "brandbot"}}"##).unwrap();
+        assert_eq!(map.account("twitch-irc", "brandchannel"), Some("brandbot"));
+        assert_eq!(map.account("twitch-irc", "#elsewhere"), Some("mainbot"));
+        assert_eq!(map.account("discord", "general"), None);
+
+        map.set("twitch-irc", "*", None);
+        assert_eq!(map.account("twitch-irc", "elsewhere"), None);
+        map.set("twitch-irc", "brandchannel", None);
+        assert_eq!(map.entries().count(), 0);
+        assert_eq!(map.to_json(), "{}");
+
+        assert!(IdentityMap::parse(r#"{"twitch-irc": "mainbot"}"#).is_err());
+    }
+}
@@ -0,0 +1,398 @@
+// File: maowbot-core/src/services/chat_load.rs
+//
+// Load generator for the chat path. Synthetic messages go through
+// `MessageService::process_incoming_message` at a fixed rate, exactly like a
+// platform's chat would send them, and a subscriber on the event bus waits for
+// each one's `ChatMessage` event. The report has how long the ingest calls
+// took, how long each message took to reach the bus subscriber (end to end)
+// and how many never arrived.
+//
+// Generated messages carry `loadtest_*` metadata. Their senders are throwaway
+// users that never reach the database, the chat logger and responders skip
+// them, and the channel is dropped from the chat cache when the run ends. Runs
+// refuse channels with real chat, so nothing a broadcaster sees is touched.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use parking_lot::Mutex;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+use uuid::Uuid;
+use maowbot_common::models::user::User;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::services::message_service::MessageService;
+use crate::Error;
+
+pub const MAX_RATE_PER_SEC: u32 = 10_000;
+pub const MAX_DURATION: Duration = Duration::from_secs(600);
+pub const MAX_USERS: u32 = 1_000;
+/// Producer tick; messages due since the last tick are sent together.
+const TICK: Duration = Duration::from_millis(10);
+const PROGRESS_EVERY: Duration = Duration::from_secs(1);
+/// Ingest calls running at once. When the pipeline can't keep up the
+/// producer waits here and falls behind the requested rate.
+const MAX_IN_FLIGHT: usize = 4096;
+/// Event bus buffer of the collecting subscriber.
+const COLLECTOR_BUFFER: usize = 65_536;
+
+/// Metadata keys that tag generated messages.
+const RUN_KEY: &str = "loadtest_run";
+const SEQ_KEY: &str = "loadtest_seq";
+const USER_KEY: &str = "loadtest_user";
+
+/// Whether chat event metadata marks a generated message.
+pub fn is_generated(metadata: &serde_json::Map<String, serde_json::Value>) -> bool {
+    metadata.contains_key(RUN_KEY)
+}
+
+/// The throwaway sender of a generated message, so ingest doesn't create a real user.
+pub(crate) fn generated_user(metadata: &[String], display_name: Option<&str>) -> Option<User> {
+    let user_id = metadata.iter()
+        .find_map(|m| m.strip_prefix(USER_KEY)?.strip_prefix(':'))?
+        .parse()
+        .ok()?;
+    let now = Utc::now();
+    Some(User {
+        user_id,
+        global_username: display_name.map(str::to_string),
+        created_at: now,
+        last_seen: now,
+        is_active: true,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatLoadConfig {
+    pub platform: String,
+    pub channel: String,
+    /// Messages per second.
+    pub rate_per_sec: u32,
+    pub duration: Duration,
+    /// Distinct senders; messages rotate through them.
+    pub users: u32,
+    /// How long to wait for outstanding events after the last message.
+    pub drain_timeout: Duration,
+}
+
+impl Default for ChatLoadConfig {
+    fn default() -> Self {
+        Self {
+            platform: "twitch-irc".to_string(),
+            channel: "#maowbot-loadtest".to_string(),
+            rate_per_sec: 100,
+            duration: Duration::from_secs(10),
+            users: 50,
+            drain_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl ChatLoadConfig {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.rate_per_sec == 0 || self.rate_per_sec > MAX_RATE_PER_SEC {
+            return Err(Error::ValidationError(format!(
+                "Rate must be between 1 and {} messages per second", MAX_RATE_PER_SEC
+            )));
+        }
+        if self.duration.is_zero() || self.duration > MAX_DURATION {
+            return Err(Error::ValidationError(format!(
+                "Duration must be between 1 and {} seconds", MAX_DURATION.as_secs()
+            )));
+        }
+        if self.users == 0 || self.users > MAX_USERS {
+            return Err(Error::ValidationError(format!("Users must be between 1 and {}", MAX_USERS)));
+        }
+        if self.channel.trim().is_empty() {
+            return Err(Error::ValidationError("Channel can't be empty".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn total_messages(&self) -> u64 {
+        (self.duration.as_secs_f64() * self.rate_per_sec as f64).ceil() as u64
+    }
+}
+
+/// Counters sent about once a second while a run is going.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChatLoadProgress {
+    pub elapsed: Duration,
+    pub sent: u64,
+    pub delivered: u64,
+    pub errors: u64,
+    pub in_flight: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let total: Duration = samples.iter().sum();
+        Self {
+            count: samples.len(),
+            mean: total / samples.len() as u32,
+            p50: percentile(&samples, 50.0),
+            p90: percentile(&samples, 90.0),
+            p99: percentile(&samples, 99.0),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChatLoadReport {
+    pub sent: u64,
+    /// Messages whose event reached the bus subscriber.
+    pub delivered: u64,
+    /// Messages ingested without error whose event never arrived.
+    pub dropped: u64,
+    pub errors: u64,
+    pub first_error: Option<String>,
+    pub peak_in_flight: usize,
+    pub elapsed: Duration,
+    /// Messages per second actually sent; below the requested rate when the
+    /// ingest path couldn't keep up.
+    pub achieved_rate: f64,
+    /// Duration of each `process_incoming_message` call.
+    pub ingest: LatencySummary,
+    /// From the start of the ingest call until the event bus delivered it.
+    pub end_to_end: LatencySummary,
+}
+
+#[derive(Default)]
+struct RunState {
+    /// Send time of each message whose event hasn't arrived yet.
+    pending: HashMap<u64, Instant>,
+    delivered: u64,
+    errors: u64,
+    first_error: Option<String>,
+    ingest: Vec<Duration>,
+    end_to_end: Vec<Duration>,
+}
+
+/// Sends `config.total_messages()` synthetic chat messages through the
+/// message service and waits for their events. Messages never start with
+/// '!', so commands don't answer them; pipelines and the chat cache see them,
+/// the chat logger and responders don't. Fails for channels with real chat.
+pub async fn run_chat_load(
+    message_service: Arc<MessageService>,
+    event_bus: Arc<EventBus>,
+    config: ChatLoadConfig,
+    progress: Option<mpsc::Sender<ChatLoadProgress>>,
+) -> Result<ChatLoadReport, Error> {
+    config.validate()?;
+    if message_service.is_known_channel(&config.platform, &config.channel).await? {
+        return Err(Error::ValidationError(format!(
+            "{} {} has real chat; load tests need a channel the bot doesn't use", config.platform, config.channel
+        )));
+    }
+    let run_id = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let total = config.total_messages();
+    info!(
+        "Chat load test {} started: {} msg/s for {}s to {} {} ({} messages)",
+        run_id, config.rate_per_sec, config.duration.as_secs(), config.platform, config.channel, total
+    );
+
+    let user_ids: Vec<Uuid> = (0..config.users).map(|_| Uuid::new_v4()).collect();
+    let state = Arc::new(Mutex::new(RunState::default()));
+    let mut events = event_bus.subscribe(Some(COLLECTOR_BUFFER)).await;
+    let collector = tokio::spawn({
+        let state = state.clone();
+        let run_id = run_id.clone();
+        async move {
+            while let Some(event) = events.recv().await {
+                let BotEvent::ChatMessage { metadata, .. } = event else { continue };
+                if metadata.get(RUN_KEY).and_then(|v| v.as_str()) != Some(run_id.as_str()) {
+                    continue;
+                }
+                let Some(seq) = metadata.get(SEQ_KEY)
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.parse::<u64>().ok())
+                else { continue };
+                let mut state = state.lock();
+                if let Some(sent_at) = state.pending.remove(&seq) {
+                    state.end_to_end.push(sent_at.elapsed());
+                    state.delivered += 1;
+                }
+            }
+        }
+    });
+
+    let semaphore = Arc::new(Semaphore::new(MAX_IN_FLIGHT));
+    let mut tasks = JoinSet::new();
+    let mut ticker = tokio::time::interval(TICK);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let started = Instant::now();
+    let mut next_progress = PROGRESS_EVERY;
+    let mut peak_in_flight = 0;
+    let mut sent = 0u64;
+
+    while sent < total {
+        ticker.tick().await;
+        let due = ((started.elapsed().as_secs_f64() * config.rate_per_sec as f64) as u64 + 1).min(total);
+        while sent < due {
+            let permit = semaphore.clone().acquire_owned().await
+                .map_err(|e| Error::Internal(e.to_string()))?;
+            let seq = sent;
+            let user = (seq % config.users as u64) as usize;
+            let message_service = message_service.clone();
+            let state = state.clone();
+            let platform = config.platform.clone();
+            let channel = config.channel.clone();
+            let metadata = vec![
+                format!("{}:{}", RUN_KEY, run_id),
+                format!("{}:{}", SEQ_KEY, seq),
+                format!("{}:{}", USER_KEY, user_ids[user]),
+            ];
+            tasks.spawn(async move {
+                let user_id = format!("loadtest-{}", user);
+                let display_name = format!("loadtest_{}", user);
+                let text = format!("load test message {} from {}", seq, display_name);
+                let sent_at = Instant::now();
+                state.lock().pending.insert(seq, sent_at);
+                let result = message_service
+                    .process_incoming_message(&platform, &channel, &user_id, Some(&display_name), &[], &text, &metadata)
+                    .await;
+                let took = sent_at.elapsed();
+                let mut state = state.lock();
+                state.ingest.push(took);
+                if let Err(e) = result {
+                    state.errors += 1;
+                    state.pending.remove(&seq);
+                    state.first_error.get_or_insert_with(|| e.to_string());
+                }
+                drop(permit);
+            });
+            sent += 1;
+        }
+        while tasks.try_join_next().is_some() {}
+        peak_in_flight = peak_in_flight.max(MAX_IN_FLIGHT - semaphore.available_permits());
+
+        if started.elapsed() >= next_progress {
+            next_progress += PROGRESS_EVERY;
+            if let Some(tx) = &progress {
+                let snapshot = {
+                    let state = state.lock();
+                    ChatLoadProgress {
+                        elapsed: started.elapsed(),
+                        sent,
+                        delivered: state.delivered,
+                        errors: state.errors,
+                        in_flight: MAX_IN_FLIGHT - semaphore.available_permits(),
+                    }
+                };
+                // A slow reader skips updates rather than slowing the run down
+                let _ = tx.try_send(snapshot);
+            }
+        }
+    }
+    let sending_time = started.elapsed();
+
+    while tasks.join_next().await.is_some() {}
+    let drain_deadline = Instant::now() + config.drain_timeout;
+    while !state.lock().pending.is_empty() && Instant::now() < drain_deadline {
+        tokio::time::sleep(TICK).await;
+    }
+    collector.abort();
+    let elapsed = started.elapsed();
+    message_service.forget_channel(&config.platform, &config.channel);
+
+    let state = std::mem::take(&mut *state.lock());
+    let report = ChatLoadReport {
+        sent,
+        delivered: state.delivered,
+        dropped: state.pending.len() as u64,
+        errors: state.errors,
+        first_error: state.first_error,
+        peak_in_flight,
+        elapsed,
+        achieved_rate: sent as f64 / sending_time.as_secs_f64().max(f64::EPSILON),
+        ingest: LatencySummary::from_samples(state.ingest),
+        end_to_end: LatencySummary::from_samples(state.end_to_end),
+    };
+    if report.dropped > 0 || report.errors > 0 {
+        warn!(
+            "Chat load test {} finished: {} sent, {} delivered, {} dropped, {} errors",
+            run_id, report.sent, report.delivered, report.dropped, report.errors
+        );
+    } else {
+        info!(
+            "Chat load test {} finished: {} sent at {:.0} msg/s, end-to-end p99 {:?}",
+            run_id, report.sent, report.achieved_rate, report.end_to_end.p99
+        );
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarizes_latencies_by_nearest_rank() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(samples);
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p90, Duration::from_millis(90));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.max, Duration::from_millis(100));
+        assert_eq!(summary.mean, Duration::from_micros(50_500));
+
+        assert_eq!(LatencySummary::from_samples(Vec::new()), LatencySummary::default());
+        assert_eq!(percentile(&[Duration::from_millis(7)], 99.0), Duration::from_millis(7));
+    }
+
+    #[test]
+    fn test_validates_limits() {
+        assert!(ChatLoadConfig::default().validate().is_ok());
+        assert_eq!(ChatLoadConfig::default().total_messages(), 1000);
+        let too_fast = ChatLoadConfig { rate_per_sec: MAX_RATE_PER_SEC + 1, ..Default::default() };
+        assert!(too_fast.validate().is_err());
+        let no_users = ChatLoadConfig { users: 0, ..Default::default() };
+        assert!(no_users.validate().is_err());
+        let too_many_users = ChatLoadConfig { users: MAX_USERS + 1, ..Default::default() };
+        assert!(too_many_users.validate().is_err());
+    }
+
+    #[test]
+    fn test_generated_messages_get_a_throwaway_user() {
+        let user_id = Uuid::new_v4();
+        let metadata = vec![format!("{}:abc", RUN_KEY), format!("{}:{}", USER_KEY, user_id)];
+        let user = generated_user(&metadata, Some("loadtest_1")).unwrap();
+        assert_eq!(user.user_id, user_id);
+        assert_eq!(user.global_username.as_deref(), Some("loadtest_1"));
+        assert!(generated_user(&["message_id:1".to_string()], None).is_none());
+
+        let mut event_metadata = serde_json::Map::new();
+        assert!(!is_generated(&event_metadata));
+        event_metadata.insert(RUN_KEY.into(), "abc".into());
+        assert!(is_generated(&event_metadata));
+    }
+}
@@ -0,0 +1,192 @@
+// File: maowbot-core/src/services/chat_summarizer.rs
+//
+// Keeps the AI aware of a long stream without replaying all of it. Chat is
+// buffered per channel, and every `ai.summary.interval_minutes` each channel
+// with at least `ai.summary.min_messages` new lines is summarized by the AI
+// into a few sentences stored in its MemoryManager under the channel's key.
+// The agent adds the latest of those summaries to its context when it
+// answers in that channel.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use maowbot_ai::plugins::ai_service::AiService;
+use maowbot_ai::traits::ChatMessage;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::settings::SettingsRegistry;
+
+/// Lines kept per channel between summaries; older ones are dropped.
+const MAX_BUFFERED_LINES: usize = 400;
+/// Transcript characters sent per summary, newest kept.
+const MAX_TRANSCRIPT_CHARS: usize = 8_000;
+const SUMMARY_TIMEOUT: StdDuration = StdDuration::from_secs(60);
+
+const SUMMARY_PROMPT: &str = "You keep a live stream chatbot's memory. Summarize this stretch of chat \
+in 2-4 short sentences: what was talked about, anything notable that happened (raids, giveaways, \
+big moments), running jokes, and who was involved. Plain text, no preamble.";
+
+/// One chat line waiting to be summarized.
+#[derive(Debug, Clone)]
+pub struct BufferedLine {
+    pub at: DateTime<Utc>,
+    pub user: String,
+    pub text: String,
+}
+
+/// "HH:MM name: text" lines, dropping the oldest past `max_chars`.
+pub fn transcript(lines: &[BufferedLine], max_chars: usize) -> String {
+    let mut kept: Vec<String> = Vec::new();
+    let mut total = 0;
+    for line in lines.iter().rev() {
+        let formatted = format!("{} {}: {}", line.at.format("%H:%M"), line.user, line.text);
+        total += formatted.chars().count() + 1;
+        if total > max_chars && !kept.is_empty() {
+            break;
+        }
+        kept.push(formatted);
+    }
+    kept.reverse();
+    kept.join("\n")
+}
+
+pub struct ChatSummarizer {
+    ai_service: Option<Arc<AiService>>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    /// channel => lines since its last summary
+    buffers: Mutex<HashMap<String, VecDeque<BufferedLine>>>,
+}
+
+impl ChatSummarizer {
+    pub fn new(
+        ai_service: Option<Arc<AiService>>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self { ai_service, event_bus, settings, buffers: Mutex::new(HashMap::new()) }
+    }
+
+    fn enabled(&self) -> bool {
+        self.ai_service.is_some() && self.settings.get_bool("ai.summary.enabled").unwrap_or(false)
+    }
+
+    /// Buffers chat as it arrives and summarizes every `ai.summary.interval_minutes`.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                let minutes = service.settings.get_u64("ai.summary.interval_minutes").unwrap_or(15).max(1);
+                let next_run = tokio::time::sleep(StdDuration::from_secs(minutes * 60));
+                tokio::pin!(next_run);
+                loop {
+                    tokio::select! {
+                        maybe_event = rx.recv() => match maybe_event {
+                            Some(event) => service.handle_event(event),
+                            None => return,
+                        },
+                        _ = &mut next_run => break,
+                        Ok(_) = shutdown_rx.changed() => {
+                            if *shutdown_rx.borrow() {
+                                debug!("[ChatSummary] stopped");
+                                return;
+                            }
+                        }
+                    }
+                }
+                service.summarize_all().await;
+            }
+        });
+    }
+
+    fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { channel, user, text, timestamp, metadata, .. } = event else { return };
+        if !self.enabled() {
+            return;
+        }
+        let user = metadata.get("username")
+            .and_then(|v| v.as_str())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .unwrap_or(user);
+        let mut buffers = self.buffers.lock();
+        let buffer = buffers.entry(channel.trim_start_matches('#').to_lowercase()).or_default();
+        buffer.push_back(BufferedLine { at: timestamp, user, text });
+        while buffer.len() > MAX_BUFFERED_LINES {
+            buffer.pop_front();
+        }
+    }
+
+    /// Summarizes every channel with enough new chat.
+    pub async fn summarize_all(&self) {
+        let Some(ai) = self.ai_service.clone() else { return };
+        if !self.enabled() || !ai.is_enabled().await {
+            return;
+        }
+        let min_messages = self.settings.get_u64("ai.summary.min_messages").unwrap_or(20) as usize;
+        let ready: Vec<(String, Vec<BufferedLine>)> = {
+            let mut buffers = self.buffers.lock();
+            buffers.iter_mut()
+                .filter(|(_, lines)| lines.len() >= min_messages)
+                .map(|(channel, lines)| (channel.clone(), lines.drain(..).collect()))
+                .collect()
+        };
+        for (channel, lines) in ready {
+            if let Err(e) = self.summarize(&ai, &channel, &lines).await {
+                warn!("[ChatSummary] could not summarize #{}: {}", channel, e);
+                // Try again next round, ahead of whatever arrived meanwhile
+                let mut buffers = self.buffers.lock();
+                let buffer = buffers.entry(channel).or_default();
+                for line in lines.into_iter().rev() {
+                    buffer.push_front(line);
+                }
+                while buffer.len() > MAX_BUFFERED_LINES {
+                    buffer.pop_front();
+                }
+            }
+        }
+    }
+
+    async fn summarize(&self, ai: &AiService, channel: &str, lines: &[BufferedLine]) -> anyhow::Result<()> {
+        let (Some(first), Some(last)) = (lines.first(), lines.last()) else { return Ok(()) };
+        let messages = vec![
+            ChatMessage { role: "system".to_string(), content: SUMMARY_PROMPT.to_string() },
+            ChatMessage { role: "user".to_string(), content: transcript(lines, MAX_TRANSCRIPT_CHARS) },
+        ];
+        let summary = tokio::time::timeout(SUMMARY_TIMEOUT, ai.client().chat(messages)).await
+            .map_err(|_| anyhow::anyhow!("the AI did not answer in time"))??;
+        let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+        if summary.is_empty() {
+            return Ok(());
+        }
+        let entry = format!(
+            "[{}–{} UTC, {} messages] {}",
+            first.at.format("%Y-%m-%d %H:%M"), last.at.format("%H:%M"), lines.len(), summary
+        );
+        ai.client().memory().store_channel_summary(channel, &entry).await?;
+        info!("[ChatSummary] summarized {} line(s) of #{}", lines.len(), channel);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_transcript_keeps_the_newest_lines() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 2, 20, 5, 0).unwrap();
+        let lines: Vec<BufferedLine> = ["first", "second", "third"].iter()
+            .map(|text| BufferedLine { at, user: "viewer".to_string(), text: text.to_string() })
+            .collect();
+        assert_eq!(transcript(&lines, 1_000), "20:05 viewer: first\n20:05 viewer: second\n20:05 viewer: third");
+        assert_eq!(transcript(&lines, 45), "20:05 viewer: second\n20:05 viewer: third");
+    }
+}
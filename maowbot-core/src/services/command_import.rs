@@ -0,0 +1,585 @@
+//! Imports the custom commands of other chat bots from their exports:
+//! Nightbot, StreamElements and Fossabot, as JSON or CSV. Text commands
+//! become command rows answering with a `response`; variables are
+//! translated where we have an equivalent and reported where we don't.
+//! Timers are read and listed, but there's nothing to import them into yet.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use maowbot_common::models::Command;
+use maowbot_common::traits::repository_traits::CommandRepository;
+
+use crate::services::twitch::builtin_commands::find_builtin;
+use crate::Error;
+
+type Entry = Map<String, Value>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    Nightbot,
+    StreamElements,
+    Fossabot,
+}
+
+impl ImportSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportSource::Nightbot => "nightbot",
+            ImportSource::StreamElements => "streamelements",
+            ImportSource::Fossabot => "fossabot",
+        }
+    }
+
+    /// How the bot writes a variable: `$(user)` or `${user}`.
+    fn delimiters(&self) -> (&'static str, char, char) {
+        match self {
+            ImportSource::StreamElements => ("${", '{', '}'),
+            ImportSource::Nightbot | ImportSource::Fossabot => ("$(", '(', ')'),
+        }
+    }
+
+    /// Our placeholder for one of the bot's variables, by what's inside the
+    /// delimiters.
+    fn placeholder(&self, variable: &str) -> Option<&'static str> {
+        let variable = variable.trim().to_lowercase();
+        match self {
+            ImportSource::StreamElements => match variable.as_str() {
+                "user" | "user.name" | "sender" | "sender.name" => Some("{user}"),
+                "touser" => Some("{touser}"),
+                "channel" | "channel.name" => Some("{channel}"),
+                "1:" => Some("{args}"),
+                _ => None,
+            },
+            ImportSource::Nightbot | ImportSource::Fossabot => match variable.as_str() {
+                "user" | "username" | "sender" => Some("{user}"),
+                "touser" => Some("{touser}"),
+                "channel" => Some("{channel}"),
+                "query" => Some("{args}"),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ImportSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "nightbot" => Ok(ImportSource::Nightbot),
+            "streamelements" | "se" => Ok(ImportSource::StreamElements),
+            "fossabot" => Ok(ImportSource::Fossabot),
+            other => Err(Error::Parse(format!(
+                "Unknown bot '{}', expected nightbot, streamelements or fossabot", other
+            ))),
+        }
+    }
+}
+
+/// A text command read from an export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedCommand {
+    pub name: String,
+    /// The reply, variables translated
+    pub response: String,
+    pub min_role: String,
+    pub cooldown_seconds: i32,
+    pub user_cooldown_seconds: i32,
+    pub enabled: bool,
+}
+
+impl ImportedCommand {
+    fn to_command(&self, platform: &str) -> Command {
+        let now = Utc::now();
+        Command {
+            command_id: Uuid::new_v4(),
+            platform: platform.to_string(),
+            command_name: self.name.clone(),
+            min_role: self.min_role.clone(),
+            is_active: self.enabled,
+            created_at: now,
+            updated_at: now,
+            cooldown_seconds: self.cooldown_seconds,
+            user_cooldown_seconds: self.user_cooldown_seconds,
+            role_cooldowns: Default::default(),
+            cooldown_bypass_mods: false,
+            reply_privately: false,
+            reply_to_message: false,
+            response: Some(self.response.clone()),
+            cooldown_warnonce: false,
+            respond_with_credential: None,
+            stream_online_only: false,
+            stream_offline_only: false,
+            active_credential_id: None,
+        }
+    }
+}
+
+/// A timed message read from an export; listed, not imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTimer {
+    pub name: String,
+    /// Variables translated like command responses
+    pub messages: Vec<String>,
+    /// Minutes between posts, when the export says
+    pub interval_minutes: Option<i64>,
+    pub enabled: bool,
+}
+
+/// Everything read from one export, before anything is written.
+#[derive(Debug, Clone, Default)]
+pub struct ImportPlan {
+    pub commands: Vec<ImportedCommand>,
+    pub timers: Vec<ImportedTimer>,
+    /// Entries that couldn't be read, as "name: reason"
+    pub skipped: Vec<String>,
+    /// Variables that were translated, e.g. "$(user)" => "{user}"
+    pub mapped: BTreeMap<String, String>,
+    /// Variables with no equivalent, left in the text as they were, with the
+    /// commands and timers using them
+    pub unsupported: BTreeMap<String, Vec<String>>,
+}
+
+/// What happened to one imported command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStatus {
+    Created,
+    /// Would be created; dry run
+    New,
+    /// A command with this name already exists on the platform and was left alone
+    Exists,
+    /// The name belongs to a built-in command, whose logic would win
+    BuiltIn,
+}
+
+impl ImportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportStatus::Created => "created",
+            ImportStatus::New => "new",
+            ImportStatus::Exists => "exists",
+            ImportStatus::BuiltIn => "builtin",
+        }
+    }
+}
+
+/// Reads an export: JSON (a list of entries, or an object with "commands" and
+/// "timers" lists, as the bots' APIs return them) or CSV with a header row.
+/// Entries with an interval are timers.
+pub fn parse_export(source: ImportSource, content: &str) -> Result<ImportPlan, Error> {
+    let content = content.trim_start_matches('\u{feff}').trim();
+    if content.is_empty() {
+        return Err(Error::Parse("The export is empty".into()));
+    }
+    let (commands, timers) = if content.starts_with('{') || content.starts_with('[') {
+        json_entries(content)?
+    } else {
+        csv_entries(content)?
+    };
+
+    let mut plan = ImportPlan::default();
+    for entry in &commands {
+        read_command(source, entry, &mut plan);
+    }
+    for entry in &timers {
+        read_timer(source, entry, &mut plan);
+    }
+    Ok(plan)
+}
+
+/// Creates `commands` on `platform`, leaving existing commands and built-in
+/// names alone. With `dry_run` nothing is written. Statuses are in the order
+/// of `commands`.
+pub async fn import_commands(
+    repo: &dyn CommandRepository,
+    platform: &str,
+    commands: &[ImportedCommand],
+    dry_run: bool,
+) -> Result<Vec<ImportStatus>, Error> {
+    let mut statuses = Vec::with_capacity(commands.len());
+    // Aliases can repeat a name within one export
+    let mut taken = HashSet::new();
+    for cmd in commands {
+        let status = if find_builtin(&cmd.name).is_some() {
+            ImportStatus::BuiltIn
+        } else if !taken.insert(cmd.name.clone())
+            || repo.get_command_by_name(platform, &cmd.name).await?.is_some()
+        {
+            ImportStatus::Exists
+        } else if dry_run {
+            ImportStatus::New
+        } else {
+            repo.create_command(&cmd.to_command(platform)).await?;
+            ImportStatus::Created
+        };
+        statuses.push(status);
+    }
+    Ok(statuses)
+}
+
+fn json_entries(content: &str) -> Result<(Vec<Entry>, Vec<Entry>), Error> {
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| Error::Parse(format!("Invalid JSON: {}", e)))?;
+    let objects = |v: Option<&Value>| -> Vec<Entry> {
+        v.and_then(Value::as_array)
+            .map(|a| a.iter().filter_map(|e| e.as_object().cloned()).collect())
+            .unwrap_or_default()
+    };
+    match &value {
+        Value::Array(_) => Ok(split_timers(objects(Some(&value)))),
+        Value::Object(obj) if obj.contains_key("commands") || obj.contains_key("timers") => {
+            Ok((objects(obj.get("commands")), objects(obj.get("timers"))))
+        }
+        _ => Err(Error::Parse(
+            "Expected a list of commands, or an object with \"commands\" and \"timers\" lists".into(),
+        )),
+    }
+}
+
+fn csv_entries(content: &str) -> Result<(Vec<Entry>, Vec<Entry>), Error> {
+    let mut rows = csv_rows(content).into_iter();
+    let header: Vec<String> = rows.next()
+        .ok_or_else(|| Error::Parse("The CSV has no header row".into()))?
+        .into_iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    let entries = rows
+        .filter(|row| row.iter().any(|f| !f.trim().is_empty()))
+        .map(|row| header.iter().cloned().zip(row.into_iter().map(Value::String)).collect())
+        .collect();
+    Ok(split_timers(entries))
+}
+
+fn split_timers(entries: Vec<Entry>) -> (Vec<Entry>, Vec<Entry>) {
+    entries.into_iter().partition(|e| field(e, &["interval"]).is_none())
+}
+
+/// Splits CSV into rows of fields. Quoted fields may hold commas, line
+/// breaks and doubled quotes.
+fn csv_rows(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            '\r' if !quoted => {}
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// The first of `keys` the entry has. Keys are compared ignoring case, '_'
+/// and spaces, so "coolDown", "cooldown" and "Cool Down" are the same.
+fn field<'a>(entry: &'a Entry, keys: &[&str]) -> Option<&'a Value> {
+    let normalize = |k: &str| k.chars().filter(|c| *c != '_' && *c != ' ').collect::<String>().to_lowercase();
+    keys.iter().find_map(|key| {
+        entry.iter().find(|(k, _)| normalize(k) == normalize(key)).map(|(_, v)| v)
+    })
+}
+
+fn text(entry: &Entry, keys: &[&str]) -> Option<String> {
+    match field(entry, keys)? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn number(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        Value::String(s) => s.trim().parse::<f64>().ok().map(|f| f as i64),
+        _ => None,
+    }
+}
+
+fn flag(entry: &Entry, keys: &[&str]) -> Option<bool> {
+    match field(entry, keys)? {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) => match s.trim().to_lowercase().as_str() {
+            "true" | "yes" | "1" | "enabled" | "on" => Some(true),
+            "false" | "no" | "0" | "disabled" | "off" => Some(false),
+            _ => None,
+        },
+        Value::Number(n) => n.as_i64().map(|n| n != 0),
+        _ => None,
+    }
+}
+
+fn seconds(value: Option<i64>) -> i32 {
+    value.unwrap_or(0).clamp(0, i32::MAX as i64) as i32
+}
+
+/// Our role for a bot's permission level: a name, or StreamElements'
+/// numeric access level (100 everyone, 250 subscriber, 300 regular,
+/// 400 VIP, 500 moderator, 1500 broadcaster).
+fn map_role(level: &Value) -> Result<&'static str, String> {
+    let tier = |n: i64| match n {
+        ..=100 => "everyone",
+        101..=250 => "subscriber",
+        251..=400 => "vip",
+        401..=1000 => "moderator",
+        _ => "broadcaster",
+    };
+    match level {
+        Value::Number(_) => number(level).map(tier).ok_or_else(|| format!("unknown permission level {}", level)),
+        Value::String(s) => {
+            let name: String = s.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+            match name.as_str() {
+                "" | "everyone" | "all" | "public" => Ok("everyone"),
+                "subscriber" | "subscribers" | "sub" | "subs" => Ok("subscriber"),
+                "regular" | "regulars" | "vip" | "vips" | "twitchvip" => Ok("vip"),
+                "moderator" | "moderators" | "mod" | "mods" | "supermoderator" => Ok("moderator"),
+                "owner" | "broadcaster" | "streamer" | "admin" => Ok("broadcaster"),
+                _ => name.parse::<i64>().map(tier).map_err(|_| format!("unknown permission level '{}'", s)),
+            }
+        }
+        Value::Null => Ok("everyone"),
+        other => Err(format!("unknown permission level {}", other)),
+    }
+}
+
+/// The global and per-user cooldowns in seconds: a StreamElements
+/// `{"global": N, "user": N}` object or separate fields.
+fn cooldowns(entry: &Entry) -> (i32, i32) {
+    match field(entry, &["cooldown"]) {
+        Some(Value::Object(cd)) => (
+            seconds(cd.get("global").and_then(number)),
+            seconds(cd.get("user").and_then(number)),
+        ),
+        _ => (
+            seconds(field(entry, &["cooldown", "globalCooldown"]).and_then(number)),
+            seconds(field(entry, &["userCooldown"]).and_then(number)),
+        ),
+    }
+}
+
+/// Length of the variable body starting right after its opening delimiter,
+/// allowing nested parentheses or braces; None when it never closes.
+fn variable_len(rest: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 1;
+    for (i, c) in rest.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Translates the variables in `text` into our placeholders. Returns the
+/// text and the variables with no equivalent (by name, e.g. "$(urlfetch)"),
+/// which stay in the text as they were.
+fn translate(source: ImportSource, text: &str, mapped: &mut BTreeMap<String, String>) -> (String, Vec<String>) {
+    let (prefix, open, close) = source.delimiters();
+    let mut out = String::with_capacity(text.len());
+    let mut unsupported = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(prefix) {
+        out.push_str(&rest[..start]);
+        let body_start = start + prefix.len();
+        let Some(len) = variable_len(&rest[body_start..], open, close) else {
+            rest = &rest[start..];
+            break;
+        };
+        let body = &rest[body_start..body_start + len];
+        let variable = &rest[start..body_start + len + close.len_utf8()];
+        match source.placeholder(body) {
+            Some(placeholder) => {
+                out.push_str(placeholder);
+                mapped.insert(variable.to_string(), placeholder.to_string());
+            }
+            None => {
+                out.push_str(variable);
+                let name = body.split_whitespace().next().unwrap_or_default();
+                let name = format!("{}{}{}", prefix, name, close);
+                if !unsupported.contains(&name) {
+                    unsupported.push(name);
+                }
+            }
+        }
+        rest = &rest[body_start + len + close.len_utf8()..];
+    }
+    out.push_str(rest);
+    (out, unsupported)
+}
+
+fn note_unsupported(plan: &mut ImportPlan, variables: Vec<String>, used_by: &str) {
+    for variable in variables {
+        let users = plan.unsupported.entry(variable).or_default();
+        if !users.iter().any(|u| u == used_by) {
+            users.push(used_by.to_string());
+        }
+    }
+}
+
+/// A command name as we store it: without the bot's '!' prefix, lowercase.
+fn command_name(raw: &str) -> Result<String, String> {
+    let name = raw.trim().trim_start_matches('!').to_lowercase();
+    if name.is_empty() {
+        Err("empty command name".to_string())
+    } else if name.chars().any(char::is_whitespace) {
+        Err("command names can't contain spaces".to_string())
+    } else {
+        Ok(name)
+    }
+}
+
+fn read_command(source: ImportSource, entry: &Entry, plan: &mut ImportPlan) {
+    let Some(raw_name) = text(entry, &["name", "command"]) else {
+        plan.skipped.push("(unnamed): no command name".to_string());
+        return;
+    };
+    let name = match command_name(&raw_name) {
+        Ok(name) => name,
+        Err(e) => {
+            plan.skipped.push(format!("{}: {}", raw_name, e));
+            return;
+        }
+    };
+    let Some(reply) = text(entry, &["message", "reply", "response"]) else {
+        plan.skipped.push(format!("{}: no response text", name));
+        return;
+    };
+    let min_role = match field(entry, &["userLevel", "accessLevel", "permission", "permissions", "role"]) {
+        Some(level) => match map_role(level) {
+            Ok(role) => role,
+            Err(e) => {
+                plan.skipped.push(format!("{}: {}", name, e));
+                return;
+            }
+        },
+        None => "everyone",
+    };
+    let (cooldown_seconds, user_cooldown_seconds) = cooldowns(entry);
+    let (response, unsupported) = translate(source, &reply, &mut plan.mapped);
+    note_unsupported(plan, unsupported, &format!("!{}", name));
+
+    let command = ImportedCommand {
+        name,
+        response,
+        min_role: min_role.to_string(),
+        cooldown_seconds,
+        user_cooldown_seconds,
+        enabled: flag(entry, &["enabled", "active"]).unwrap_or(true),
+    };
+    let aliases: Vec<String> = match field(entry, &["aliases"]) {
+        Some(Value::Array(a)) => a.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        Some(Value::String(s)) => s.split(',').map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+    plan.commands.push(command.clone());
+    for alias in aliases.iter().filter(|a| !a.trim().is_empty()) {
+        match command_name(alias) {
+            Ok(alias) => plan.commands.push(ImportedCommand { name: alias, ..command.clone() }),
+            Err(e) => plan.skipped.push(format!("{} (alias of !{}): {}", alias, command.name, e)),
+        }
+    }
+}
+
+/// Minutes between a timer's posts: a number of minutes, StreamElements'
+/// `{"online": N}` or a Nightbot cron string like "*/15 * * * *".
+fn interval_minutes(value: &Value) -> Option<i64> {
+    match value {
+        Value::Object(obj) => obj.get("online").and_then(number).filter(|n| *n > 0),
+        Value::String(s) if s.contains('*') => s.split_whitespace().next()?.strip_prefix("*/")?.parse().ok(),
+        other => number(other).filter(|n| *n > 0),
+    }
+}
+
+fn read_timer(source: ImportSource, entry: &Entry, plan: &mut ImportPlan) {
+    let name = text(entry, &["name"]).unwrap_or_else(|| "(unnamed)".to_string());
+    let raw: Vec<String> = match field(entry, &["messages"]) {
+        Some(Value::Array(a)) => a.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        _ => text(entry, &["message", "response"]).into_iter().collect(),
+    };
+    let mut messages = Vec::with_capacity(raw.len());
+    for message in raw.iter().filter(|m| !m.trim().is_empty()) {
+        let (message, unsupported) = translate(source, message, &mut plan.mapped);
+        note_unsupported(plan, unsupported, &format!("timer {}", name));
+        messages.push(message);
+    }
+    plan.timers.push(ImportedTimer {
+        interval_minutes: field(entry, &["interval"]).and_then(interval_minutes),
+        enabled: flag(entry, &["enabled", "active"]).unwrap_or(true),
+        name,
+        messages,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_exports_and_translates_variables() {
+        let nightbot = r#"{
+            "_total": 2,
+            "commands": [
+                {"name": "!discord", "message": "$(user) -> discord.gg/x $(urlfetch https://a.b/$(query))", "coolDown": 30, "userLevel": "everyone"},
+                {"name": "!so", "message": "Go follow $(touser)!", "coolDown": 5, "userLevel": "moderator"},
+                {"name": "!broken", "message": "hi", "userLevel": "wizard"}
+            ],
+            "timers": [{"name": "socials", "message": "Follow $(channel)!", "interval": "*/15 * * * *", "lines": 2}]
+        }"#;
+        let plan = parse_export(ImportSource::Nightbot, nightbot).unwrap();
+        assert_eq!(plan.commands.len(), 2);
+        assert_eq!(plan.commands[0].name, "discord");
+        assert_eq!(plan.commands[0].response, "{user} -> discord.gg/x $(urlfetch https://a.b/$(query))");
+        assert_eq!(plan.commands[0].cooldown_seconds, 30);
+        assert_eq!(plan.commands[1].min_role, "moderator");
+        assert_eq!(plan.unsupported["$(urlfetch)"], vec!["!discord"]);
+        assert_eq!(plan.skipped.len(), 1);
+        assert_eq!(plan.timers[0].interval_minutes, Some(15));
+        assert_eq!(plan.timers[0].messages, vec!["Follow {channel}!"]);
+
+        let se = r#"[{"command": "hug", "reply": "${sender} hugs ${1:}", "accessLevel": 250,
+            "cooldown": {"global": 5, "user": 15}, "aliases": ["cuddle"], "enabled": false}]"#;
+        let plan = parse_export(ImportSource::StreamElements, se).unwrap();
+        let names: Vec<&str> = plan.commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["hug", "cuddle"]);
+        assert_eq!(plan.commands[0].response, "{user} hugs {args}");
+        assert_eq!(plan.commands[0].min_role, "subscriber");
+        assert_eq!((plan.commands[0].cooldown_seconds, plan.commands[0].user_cooldown_seconds), (5, 15));
+        assert!(!plan.commands[1].enabled);
+
+        let csv = "name,response,cooldown,permission\n!lurk,\"$(user) is lurking, bye\",10,Everyone\n";
+        let plan = parse_export(ImportSource::Fossabot, csv).unwrap();
+        assert_eq!(plan.commands[0].name, "lurk");
+        assert_eq!(plan.commands[0].response, "{user} is lurking, bye");
+        assert_eq!(plan.mapped["$(user)"], "{user}");
+    }
+}
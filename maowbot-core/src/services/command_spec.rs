@@ -0,0 +1,243 @@
+//! One definition per built-in command: its name, argument schema, default
+//! role and handler. Chat (`!name args`), Discord slash commands (`/name`
+//! with options) and simulated slash commands all parse their arguments
+//! against the same schema before the handler runs.
+
+use std::fmt;
+
+use futures_util::future::BoxFuture;
+use maowbot_common::models::Command;
+use maowbot_common::models::user::User;
+
+use crate::Error;
+use crate::services::twitch::command_service::CommandContext;
+
+/// Runs a command with already validated arguments; the text is sent back
+/// as the reply, split into lines at `<SPLIT>`.
+pub type CommandHandler = for<'a> fn(
+    &'a Command,
+    &'a CommandContext<'a>,
+    &'a User,
+    &'a CommandArgs,
+) -> BoxFuture<'a, Result<String, Error>>;
+
+/// Platforms every built-in can run on.
+pub const EVERYWHERE: &[&str] = &["twitch-irc", "discord", "kick"];
+/// For commands that act on the Twitch stream or its chat.
+pub const TWITCH_ONLY: &[&str] = &["twitch-irc"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// A single word
+    Word,
+    /// A chatter's name; a leading '@' is dropped. Slash commands take it as
+    /// text, since chatters are looked up by name across platforms.
+    User,
+    Integer,
+    /// One of the listed words, compared without case
+    Choice(&'static [&'static str]),
+    /// The rest of the line; only valid as the last argument
+    Text,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: ArgKind,
+    pub required: bool,
+}
+
+impl ArgSpec {
+    pub const fn required(name: &'static str, kind: ArgKind, description: &'static str) -> Self {
+        Self { name, description, kind, required: true }
+    }
+
+    pub const fn optional(name: &'static str, kind: ArgKind, description: &'static str) -> Self {
+        Self { name, description, kind, required: false }
+    }
+
+    /// Checks and normalizes one value.
+    fn accept(&self, value: &str) -> Result<String, ArgError> {
+        let invalid = || ArgError::Invalid { arg: self.name, value: value.to_string(), kind: self.kind };
+        match self.kind {
+            ArgKind::Word | ArgKind::Text => Ok(value.to_string()),
+            ArgKind::User => {
+                let name = value.trim_start_matches('@');
+                if name.is_empty() { Err(invalid()) } else { Ok(name.to_string()) }
+            }
+            ArgKind::Integer => value.parse::<i64>().map(|n| n.to_string()).map_err(|_| invalid()),
+            ArgKind::Choice(choices) => choices.iter()
+                .find(|c| c.eq_ignore_ascii_case(value))
+                .map(|c| c.to_string())
+                .ok_or_else(invalid),
+        }
+    }
+}
+
+pub struct CommandSpec {
+    pub name: &'static str,
+    /// Shown in Discord's command picker; at most 100 characters
+    pub description: &'static str,
+    pub args: &'static [ArgSpec],
+    /// Role needed where the command has no row of its own; a row's
+    /// `min_role` takes over once it exists
+    pub min_role: &'static str,
+    /// Where the command is available without a row (and registered as a
+    /// slash command, for "discord")
+    pub platforms: &'static [&'static str],
+    pub handler: CommandHandler,
+}
+
+impl CommandSpec {
+    pub fn available_on(&self, platform: &str) -> bool {
+        self.platforms.iter().any(|p| p.eq_ignore_ascii_case(platform))
+    }
+
+    /// e.g. "!set <name> <value>" or "/watchtime [user]".
+    pub fn usage(&self, prefix: &str) -> String {
+        let mut usage = format!("{}{}", prefix, self.name);
+        for arg in self.args {
+            let name = match arg.kind {
+                ArgKind::Choice(choices) => choices.join("|"),
+                _ => arg.name.to_string(),
+            };
+            if arg.required {
+                usage.push_str(&format!(" <{}>", name));
+            } else {
+                usage.push_str(&format!(" [{}]", name));
+            }
+        }
+        usage
+    }
+
+    /// Parses the text after `!name`. Words beyond the schema are ignored.
+    pub fn parse_line(&self, line: &str) -> Result<CommandArgs, ArgError> {
+        let mut rest = line.trim();
+        let mut values = Vec::new();
+        for arg in self.args {
+            let token = if arg.kind == ArgKind::Text {
+                std::mem::take(&mut rest)
+            } else {
+                let (token, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                rest = tail.trim_start();
+                token
+            };
+            if token.is_empty() {
+                if arg.required {
+                    return Err(ArgError::Missing { arg: arg.name });
+                }
+                continue;
+            }
+            values.push((arg.name, arg.accept(token)?));
+        }
+        Ok(CommandArgs { raw: line.trim().to_string(), values })
+    }
+
+    /// Takes named option values, e.g. from a slash command. `raw()` holds
+    /// them in schema order, as if they had been typed after `!name`.
+    pub fn parse_options(&self, options: &[(String, String)]) -> Result<CommandArgs, ArgError> {
+        if let Some((unknown, _)) = options.iter().find(|(name, _)| !self.args.iter().any(|a| a.name == name)) {
+            return Err(ArgError::Unknown { arg: unknown.clone() });
+        }
+        let mut values = Vec::new();
+        for arg in self.args {
+            match options.iter().find(|(name, _)| name == arg.name).map(|(_, v)| v.trim()) {
+                Some(value) if !value.is_empty() => values.push((arg.name, arg.accept(value)?)),
+                _ if arg.required => return Err(ArgError::Missing { arg: arg.name }),
+                _ => {}
+            }
+        }
+        let raw = values.iter().map(|(_, v)| v.as_str()).collect::<Vec<_>>().join(" ");
+        Ok(CommandArgs { raw, values })
+    }
+}
+
+/// A command's arguments after parsing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandArgs {
+    raw: String,
+    values: Vec<(&'static str, String)>,
+}
+
+impl CommandArgs {
+    /// For commands without a schema: only the raw text.
+    pub fn from_raw(raw: &str) -> Self {
+        Self { raw: raw.trim().to_string(), values: Vec::new() }
+    }
+
+    /// Everything after the command name.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.iter().find(|(n, _)| *n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// An `Integer` argument.
+    pub fn integer(&self, name: &str) -> Option<i64> {
+        self.get(name).and_then(|v| v.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgError {
+    Missing { arg: &'static str },
+    Invalid { arg: &'static str, value: String, kind: ArgKind },
+    Unknown { arg: String },
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgError::Missing { arg } => write!(f, "{} is missing", arg),
+            ArgError::Invalid { arg, value, kind: ArgKind::Integer } => write!(f, "{} must be a number, not '{}'", arg, value),
+            ArgError::Invalid { arg, value, kind: ArgKind::Choice(choices) } => {
+                write!(f, "{} must be one of {}, not '{}'", arg, choices.join(", "), value)
+            }
+            ArgError::Invalid { arg, value, .. } => write!(f, "'{}' is not a valid {}", value, arg),
+            ArgError::Unknown { arg } => write!(f, "unknown option {}", arg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop<'a>(_: &'a Command, _: &'a CommandContext<'a>, _: &'a User, _: &'a CommandArgs) -> BoxFuture<'a, Result<String, Error>> {
+        Box::pin(async { Ok(String::new()) })
+    }
+
+    const SPEC: CommandSpec = CommandSpec {
+        name: "demo",
+        description: "Demo",
+        args: &[
+            ArgSpec::required("who", ArgKind::User, "Someone"),
+            ArgSpec::optional("times", ArgKind::Integer, "How often"),
+            ArgSpec::optional("note", ArgKind::Text, "Anything else"),
+        ],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: noop,
+    };
+
+    #[test]
+    fn test_chat_and_options_parse_alike() {
+        let line = SPEC.parse_line("  @Bob 3 hello there ").unwrap();
+        assert_eq!(line.get("who"), Some("Bob"));
+        assert_eq!(line.integer("times"), Some(3));
+        assert_eq!(line.get("note"), Some("hello there"));
+
+        let options = [("note", "hello there"), ("who", "Bob"), ("times", "3")]
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        let parsed = SPEC.parse_options(&options).unwrap();
+        assert_eq!(parsed.raw(), "Bob 3 hello there");
+        assert_eq!(parsed.get("who"), line.get("who"));
+
+        assert_eq!(SPEC.parse_line(""), Err(ArgError::Missing { arg: "who" }));
+        assert!(matches!(SPEC.parse_line("bob often"), Err(ArgError::Invalid { arg: "times", .. })));
+        assert_eq!(SPEC.usage("/"), "/demo <who> [times] [note]");
+    }
+}
@@ -1,80 +1,176 @@
 // File: maowbot-core/src/services/discord/slashcommands/mod.rs
-
-pub mod ping;
+//! Registers the built-in commands available on Discord as slash commands and
+//! turns their interactions into `DiscordMessageEvent`s. Discord wants an
+//! answer within three seconds, so interactions are acknowledged right away
+//! and answered by `DiscordPlatform::finish_interaction` once the command has run.
 
 use std::sync::Arc;
 use twilight_http::Client as HttpClient;
 use twilight_model::{
     application::{
-        command::Command,
-        interaction::{Interaction, InteractionData},
+        command::{Command, CommandType},
+        interaction::{
+            application_command::CommandOptionValue,
+            Interaction, InteractionContextType, InteractionData, InteractionDataResolved,
+        },
     },
-    gateway::payload::incoming::InteractionCreate,
-    http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
-    id::marker::{ApplicationMarker, InteractionMarker},
+    guild::Permissions,
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::marker::ApplicationMarker,
     id::Id,
 };
-use twilight_util::builder::command::CommandBuilder;
+use twilight_util::builder::command::{CommandBuilder, IntegerBuilder, StringBuilder};
 
 use maowbot_common::error::Error;
-use crate::services::discord::slashcommands::ping::{
-    create_ping_command,
-    handle_ping_interaction,
-};
+use crate::platforms::discord::runtime::{DiscordMessageEvent, DiscordSlashCommand};
+use crate::services::command_spec::{ArgKind, CommandSpec};
+use crate::services::twitch::builtin_commands::BUILTIN_COMMANDS;
 
+/// Members with any of these count as moderators for slash commands.
+const MODERATOR_PERMISSIONS: Permissions = Permissions::ADMINISTRATOR
+    .union(Permissions::MODERATE_MEMBERS)
+    .union(Permissions::MANAGE_MESSAGES);
 
 pub async fn register_global_slash_commands(
     http: &Arc<HttpClient>,
     application_id: Id<ApplicationMarker>,
 ) -> Result<(), Error> {
-    // Build your slash commands:
-    let ping_cmd = create_ping_command().build(); // returns `Command` immediately
-    let commands = &[ping_cmd]; // If more commands, push them here.
+    let commands: Vec<Command> = BUILTIN_COMMANDS.iter()
+        .filter(|spec| spec.available_on("discord"))
+        .map(slash_command)
+        .collect();
 
     http.interaction(application_id)
-        .set_global_commands(commands)
+        .set_global_commands(&commands)
         .await
         .map_err(|e| Error::Platform(format!("Failed to register global slash commands: {e}")))?;
 
     Ok(())
 }
 
-/// Dispatch slash commands from an `InteractionCreate`.
-pub async fn handle_interaction_create(
-    http: Arc<HttpClient>,
+/// The slash command for a built-in. Commands that default to moderators are
+/// hidden from members without moderator permissions; server admins can
+/// change that under Integrations.
+pub fn slash_command(spec: &CommandSpec) -> Command {
+    let mut builder = CommandBuilder::new(spec.name, spec.description, CommandType::ChatInput)
+        .contexts([InteractionContextType::Guild, InteractionContextType::BotDm]);
+    match spec.min_role.to_lowercase().as_str() {
+        "everyone" => {}
+        "broadcaster" => builder = builder.default_member_permissions(Permissions::MANAGE_GUILD),
+        _ => builder = builder.default_member_permissions(Permissions::MODERATE_MEMBERS),
+    }
+    for arg in spec.args {
+        builder = match arg.kind {
+            ArgKind::Integer => builder.option(
+                IntegerBuilder::new(arg.name, arg.description).required(arg.required)
+            ),
+            ArgKind::Choice(choices) => builder.option(
+                StringBuilder::new(arg.name, arg.description)
+                    .required(arg.required)
+                    .choices(choices.iter().map(|c| (*c, *c)))
+            ),
+            ArgKind::Word | ArgKind::User | ArgKind::Text => builder.option(
+                StringBuilder::new(arg.name, arg.description).required(arg.required)
+            ),
+        };
+    }
+    builder.build()
+}
+
+/// Acknowledges a slash command interaction ("thinking...") and returns it as
+/// an event for the message loop. Other interactions give `None`.
+pub async fn acknowledge_slash_command(
+    http: &HttpClient,
     application_id: Id<ApplicationMarker>,
-    event: &InteractionCreate,
-) -> Result<(), Error> {
-    let interaction = &event.0;
-    let interaction_id = interaction.id;
-    let interaction_token = &interaction.token;
-
-    // Only handle ApplicationCommand interactions:
-    if let Some(InteractionData::ApplicationCommand(cmd_data)) = &interaction.data {
-        let name = cmd_data.name.as_str();
-        match name {
-            "ping" => {
-                handle_ping_interaction(&http, application_id, interaction_id, interaction_token).await?;
-            }
-            other => {
-                // For unknown commands, respond with error:
-                http.interaction(application_id)
-                    .create_response(
-                        interaction_id,
-                        interaction_token,
-                        &InteractionResponse {
-                            kind: InteractionResponseType::ChannelMessageWithSource,
-                            data: Some(InteractionResponseData {
-                                content: Some(format!("Unrecognized command: {other}")),
-                                ..Default::default()
-                            }),
-                        },
-                    )
-                    .await
-                    .ok(); // ignore error
-            }
-        }
+    interaction: &Interaction,
+) -> Result<Option<DiscordMessageEvent>, Error> {
+    let Some(InteractionData::ApplicationCommand(data)) = &interaction.data else {
+        return Ok(None);
+    };
+    let Some(author) = interaction.author() else {
+        return Ok(None);
+    };
+
+    http.interaction(application_id)
+        .create_response(
+            interaction.id,
+            &interaction.token,
+            &InteractionResponse {
+                kind: InteractionResponseType::DeferredChannelMessageWithSource,
+                data: None,
+            },
+        )
+        .await
+        .map_err(|e| Error::Platform(format!("Failed to acknowledge /{}: {e}", data.name)))?;
+
+    let channel = match (&interaction.guild_id, &interaction.channel) {
+        (Some(_), Some(ch)) => ch.name.clone().unwrap_or_else(|| ch.id.to_string()),
+        (None, Some(ch)) => format!("(DM {})", ch.id),
+        (_, None) => "(unknown)".to_string(),
+    };
+
+    let mut user_roles: Vec<String> = interaction.member.as_ref()
+        .map(|m| m.roles.iter().map(|r| r.to_string()).collect())
+        .unwrap_or_default();
+    let permissions = interaction.member.as_ref().and_then(|m| m.permissions);
+    if permissions.is_some_and(|p| p.intersects(MODERATOR_PERMISSIONS)) {
+        user_roles.push("moderator".to_string());
     }
 
-    Ok(())
+    let options: Vec<(String, String)> = data.options.iter()
+        .filter_map(|opt| option_text(&opt.value, data.resolved.as_ref()).map(|v| (opt.name.clone(), v)))
+        .collect();
+    let text = std::iter::once(format!("/{}", data.name))
+        .chain(options.iter().map(|(_, v)| v.clone()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(Some(DiscordMessageEvent {
+        channel,
+        user_id: author.id.to_string(),
+        username: author.name.clone(),
+        text,
+        user_roles,
+        guild_id: interaction.guild_id.map(|id| id.to_string()),
+        message_id: None,
+        reply_to: None,
+        slash_command: Some(DiscordSlashCommand {
+            name: data.name.clone(),
+            options,
+            interaction_token: interaction.token.clone(),
+        }),
+    }))
+}
+
+fn option_text(value: &CommandOptionValue, resolved: Option<&InteractionDataResolved>) -> Option<String> {
+    match value {
+        CommandOptionValue::String(s) => Some(s.clone()),
+        CommandOptionValue::Integer(n) => Some(n.to_string()),
+        CommandOptionValue::Number(n) => Some(n.to_string()),
+        CommandOptionValue::Boolean(b) => Some(b.to_string()),
+        CommandOptionValue::User(id) => Some(
+            resolved.and_then(|r| r.users.get(id))
+                .map(|u| u.name.clone())
+                .unwrap_or_else(|| id.to_string())
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::twitch::builtin_commands::find_builtin;
+
+    #[test]
+    fn test_mod_commands_are_hidden_by_default() {
+        let set = slash_command(find_builtin("set").unwrap());
+        assert_eq!(set.options.len(), 2);
+        assert!(set.options.iter().all(|o| o.required == Some(true)));
+        assert_eq!(set.default_member_permissions, None);
+
+        let vrchat = slash_command(find_builtin("vrchat").unwrap());
+        assert_eq!(vrchat.default_member_permissions, Some(Permissions::MODERATE_MEMBERS));
+        assert_eq!(vrchat.options[0].choices.as_ref().map(|c| c.len()), Some(2));
+    }
 }
@@ -0,0 +1,221 @@
+// File: maowbot-core/src/services/donations/mod.rs
+//
+// Ingests tips from Streamlabs and StreamElements. Each donation is
+// normalized, stored (which also drops replays), published on the bus as
+// `BotEvent::Donation` for pipelines, and pushed to plugins as a `donation`
+// GameEvent carrying TTS text and the current leaderboard for overlays.
+
+pub mod socketio;
+pub mod streamlabs;
+pub mod streamelements;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde_json::json;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use maowbot_common::models::donation::{Donation, DonationSource, DonorTotal};
+use maowbot_common::traits::repository_traits::DonationRepository;
+use maowbot_proto::plugs::{
+    plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse,
+};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::plugins::manager::PluginManager;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+use socketio::{SocketIoClient, SocketIoEvent};
+
+const LEADERBOARD_SIZE: i64 = 10;
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// The setting holding `source`'s token. Both are secret settings, so the
+/// registry keeps them encrypted and hands them back decrypted.
+fn token_key(source: DonationSource) -> &'static str {
+    match source {
+        DonationSource::Streamlabs => "donations.streamlabs.socket_token",
+        DonationSource::StreamElements => "donations.streamelements.jwt",
+    }
+}
+
+pub struct DonationService {
+    repo: Arc<dyn DonationRepository>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    connections: Mutex<HashMap<DonationSource, JoinHandle<()>>>,
+}
+
+impl DonationService {
+    pub fn new(
+        repo: Arc<dyn DonationRepository>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            repo,
+            event_bus,
+            settings,
+            plugin_manager,
+            connections: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Connects every source that has a token, and reconnects a source
+    /// whenever its token setting changes.
+    pub fn start(self: &Arc<Self>) {
+        for source in [DonationSource::Streamlabs, DonationSource::StreamElements] {
+            self.restart(source);
+            let weak = Arc::downgrade(self);
+            self.settings.watch(token_key(source), move |_, _| {
+                if let Some(service) = weak.upgrade() {
+                    service.restart(source);
+                }
+            });
+        }
+    }
+
+    fn restart(self: &Arc<Self>, source: DonationSource) {
+        let mut connections = self.connections.lock();
+        if let Some(handle) = connections.remove(&source) {
+            handle.abort();
+        }
+        let Some(token) = self.settings.get(token_key(source)).filter(|t| !t.trim().is_empty()) else {
+            debug!("[Donations] {} not configured", source);
+            return;
+        };
+        let service = self.clone();
+        connections.insert(source, tokio::spawn(async move { service.run(source, token).await }));
+    }
+
+    /// Keeps one source connected, backing off between attempts.
+    async fn run(self: Arc<Self>, source: DonationSource, token: String) {
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        let mut delay = Duration::from_secs(1);
+        loop {
+            let started = Instant::now();
+            tokio::select! {
+                res = self.connect_once(source, &token) => match res {
+                    Ok(()) => info!("[Donations] {} connection closed", source),
+                    Err(e) => warn!("[Donations] {} connection failed: {:?}", source, e),
+                },
+                _ = shutdown_rx.changed() => return,
+            }
+            if started.elapsed() > MAX_RECONNECT_DELAY {
+                delay = Duration::from_secs(1);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown_rx.changed() => return,
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    async fn connect_once(&self, source: DonationSource, token: &str) -> Result<(), Error> {
+        let url = match source {
+            DonationSource::Streamlabs => streamlabs::socket_url(token),
+            DonationSource::StreamElements => streamelements::SOCKET_URL.to_string(),
+        };
+        let mut client = SocketIoClient::connect(&url).await?;
+        let mut incoming = client.incoming.take()
+            .ok_or_else(|| Error::Platform("No incoming channel in SocketIoClient".into()))?;
+
+        while let Some(event) = incoming.recv().await {
+            let (name, data) = match event {
+                SocketIoEvent::Connected => {
+                    info!("[Donations] connected to {}", source);
+                    if source == DonationSource::StreamElements {
+                        client.emit("authenticate", streamelements::authenticate_payload(token));
+                    }
+                    continue;
+                }
+                SocketIoEvent::Event { name, data } => (name, data),
+            };
+            let donations = match (source, name.as_str()) {
+                (DonationSource::Streamlabs, "event") => streamlabs::parse_event(&data),
+                (DonationSource::StreamElements, "authenticated") => {
+                    info!("[Donations] StreamElements authenticated");
+                    continue;
+                }
+                (DonationSource::StreamElements, "unauthorized") => {
+                    return Err(Error::Auth(format!("StreamElements rejected the JWT: {}", data)));
+                }
+                (DonationSource::StreamElements, _) => streamelements::parse_event(&name, &data).into_iter().collect(),
+                _ => continue,
+            };
+            for donation in donations {
+                if let Err(e) = self.ingest(donation).await {
+                    warn!("[Donations] could not record {} donation: {:?}", source, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Normalizes, stores and announces one donation. Replays of a donation
+    /// that was already stored are dropped.
+    pub async fn ingest(&self, donation: Donation) -> Result<(), Error> {
+        let base_currency = self.settings.get("donations.base_currency").unwrap_or_else(|| "USD".to_string());
+        let rates: HashMap<String, f64> = self.settings.get_json("donations.exchange_rates").unwrap_or_default();
+        let donation = donation.normalize(&base_currency, &rates);
+
+        if !self.repo.record_donation(&donation).await? {
+            debug!("[Donations] ignoring replayed {} donation {:?}", donation.source, donation.external_id);
+            return Ok(());
+        }
+        info!("[Donations] {} donated {} via {}", donation.donor_name, donation.formatted_amount(), donation.source);
+
+        self.event_bus.publish(BotEvent::Donation(donation.clone())).await;
+
+        let leaderboard = self.leaderboard().await.unwrap_or_else(|e| {
+            warn!("[Donations] could not load leaderboard: {:?}", e);
+            vec![]
+        });
+        let payload = json!({
+            "donation": donation,
+            "tts": self.tts_text(&donation),
+            "leaderboard": leaderboard,
+        });
+        self.plugin_manager.broadcast(
+            PluginStreamResponse {
+                payload: Some(RespPayload::GameEvent(GameEvent {
+                    name: "donation".to_string(),
+                    json: payload.to_string(),
+                })),
+            },
+            None,
+        ).await;
+        Ok(())
+    }
+
+    /// Top donors over `donations.leaderboard_days` (0 = all time).
+    pub async fn leaderboard(&self) -> Result<Vec<DonorTotal>, Error> {
+        let days = self.settings.get_i64("donations.leaderboard_days").unwrap_or(0);
+        let since = (days > 0).then(|| Utc::now() - chrono::Duration::days(days));
+        self.repo.top_donors(since, LEADERBOARD_SIZE).await
+    }
+
+    /// What a TTS overlay should read, or None below `donations.tts_min_amount`.
+    /// Donations that couldn't be converted are only read when there is no minimum.
+    fn tts_text(&self, donation: &Donation) -> Option<String> {
+        let min = self.settings.get_i64("donations.tts_min_amount").unwrap_or(0) as f64;
+        let eligible = match donation.amount_base {
+            Some(amount) => amount >= min,
+            None => min <= 0.0,
+        };
+        if !eligible {
+            return None;
+        }
+        Some(match &donation.message {
+            Some(message) => format!("{} donated {}: {}", donation.donor_name, donation.formatted_amount(), message),
+            None => format!("{} donated {}", donation.donor_name, donation.formatted_amount()),
+        })
+    }
+}
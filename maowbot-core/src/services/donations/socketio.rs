@@ -0,0 +1,193 @@
+// File: maowbot-core/src/services/donations/socketio.rs
+//
+// Just enough of Socket.IO v2 (Engine.IO v3) over a websocket for the
+// Streamlabs and StreamElements realtime APIs: heartbeats, emitting events
+// and receiving `42["name", data]` frames.
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::{debug, trace, warn};
+
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SocketIoEvent {
+    /// The default namespace accepted the connection
+    Connected,
+    Event { name: String, data: Value },
+}
+
+#[derive(Debug, PartialEq)]
+enum Packet {
+    /// Engine.IO open handshake with the heartbeat interval in ms
+    Open { ping_interval: u64 },
+    Ping,
+    Pong,
+    Close,
+    Connect,
+    Disconnect,
+    Event { name: String, data: Value },
+}
+
+fn parse_packet(text: &str) -> Option<Packet> {
+    let mut chars = text.chars();
+    let kind = chars.next()?;
+    let rest = chars.as_str();
+    match kind {
+        '0' => {
+            let handshake: Value = serde_json::from_str(rest).ok()?;
+            Some(Packet::Open {
+                ping_interval: handshake.get("pingInterval").and_then(|v| v.as_u64()).unwrap_or(25_000),
+            })
+        }
+        '1' => Some(Packet::Close),
+        '2' => Some(Packet::Ping),
+        '3' => Some(Packet::Pong),
+        '4' => {
+            let mut chars = rest.chars();
+            let sio_kind = chars.next()?;
+            let payload = chars.as_str();
+            match sio_kind {
+                '0' => Some(Packet::Connect),
+                '1' => Some(Packet::Disconnect),
+                '2' => {
+                    // Skip an optional ack id before the JSON array
+                    let json = payload.trim_start_matches(|c: char| c.is_ascii_digit());
+                    let mut args = match serde_json::from_str::<Value>(json).ok()? {
+                        Value::Array(args) => args.into_iter(),
+                        _ => return None,
+                    };
+                    let name = args.next()?.as_str()?.to_string();
+                    Some(Packet::Event { name, data: args.next().unwrap_or(Value::Null) })
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+pub struct SocketIoClient {
+    outgoing: mpsc::UnboundedSender<String>,
+    /// Closes when the socket does.
+    pub incoming: Option<mpsc::Receiver<SocketIoEvent>>,
+    task: JoinHandle<()>,
+}
+
+impl SocketIoClient {
+    /// `url` is the full websocket URL, including `EIO=3&transport=websocket`.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        let (ws, _) = connect_async(url)
+            .await
+            .map_err(|e| Error::Platform(format!("Socket.IO connect error: {e}")))?;
+        let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
+        let (evt_tx, evt_rx) = mpsc::channel::<SocketIoEvent>(256);
+        let (mut sink, mut stream) = ws.split();
+
+        let task = tokio::spawn(async move {
+            // Engine.IO v3 clients send the pings; the real interval arrives with the handshake
+            let mut heartbeat = interval(Duration::from_millis(25_000));
+            heartbeat.tick().await;
+            loop {
+                tokio::select! {
+                    msg = stream.next() => {
+                        let msg = match msg {
+                            Some(Ok(m)) => m,
+                            Some(Err(e)) => {
+                                warn!("[Socket.IO] websocket error: {e}");
+                                break;
+                            }
+                            None => break,
+                        };
+                        if msg.is_close() {
+                            break;
+                        }
+                        let Message::Text(txt) = msg else { continue };
+                        match parse_packet(&txt) {
+                            Some(Packet::Open { ping_interval }) => {
+                                heartbeat = interval(Duration::from_millis(ping_interval.max(1_000)));
+                                heartbeat.tick().await;
+                            }
+                            Some(Packet::Ping) => {
+                                if sink.send(Message::text("3")).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Some(Packet::Pong) => trace!("[Socket.IO] pong"),
+                            Some(Packet::Connect) => {
+                                if evt_tx.send(SocketIoEvent::Connected).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Some(Packet::Close) | Some(Packet::Disconnect) => break,
+                            Some(Packet::Event { name, data }) => {
+                                if evt_tx.send(SocketIoEvent::Event { name, data }).await.is_err() {
+                                    break;
+                                }
+                            }
+                            None => debug!("[Socket.IO] ignoring frame: {}", txt),
+                        }
+                    }
+                    out = out_rx.recv() => {
+                        let Some(frame) = out else { break };
+                        if sink.send(Message::text(frame)).await.is_err() {
+                            break;
+                        }
+                    }
+                    _ = heartbeat.tick() => {
+                        if sink.send(Message::text("2")).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Socket.IO] connection closed");
+        });
+
+        Ok(Self {
+            outgoing: out_tx,
+            incoming: Some(evt_rx),
+            task,
+        })
+    }
+
+    pub fn emit(&self, name: &str, data: Value) {
+        let frame = format!("42{}", Value::Array(vec![Value::String(name.to_string()), data]));
+        let _ = self.outgoing.send(frame);
+    }
+}
+
+impl Drop for SocketIoClient {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_engine_io_v3_frames() {
+        assert_eq!(
+            parse_packet(r#"0{"sid":"abc","upgrades":[],"pingInterval":20000,"pingTimeout":5000}"#),
+            Some(Packet::Open { ping_interval: 20_000 })
+        );
+        assert_eq!(parse_packet("3"), Some(Packet::Pong));
+        assert_eq!(parse_packet("40"), Some(Packet::Connect));
+        assert_eq!(
+            parse_packet(r#"42["event",{"type":"donation"}]"#),
+            Some(Packet::Event { name: "event".into(), data: json!({"type": "donation"}) })
+        );
+        assert_eq!(
+            parse_packet(r#"4212["authenticated",{"channelId":"x"}]"#),
+            Some(Packet::Event { name: "authenticated".into(), data: json!({"channelId": "x"}) })
+        );
+    }
+}
@@ -0,0 +1,46 @@
+// File: maowbot-core/src/services/donations/streamelements.rs
+//
+// StreamElements realtime socket: authenticate with the account's JWT
+// (Dashboard → Account → Channels → Show secrets); tips arrive as `event`
+// frames with `type: "tip"`, and the dashboard's emulator sends `event:test`.
+
+use serde_json::{json, Value};
+use maowbot_common::models::donation::{parse_amount, Donation, DonationSource};
+
+pub const SOCKET_URL: &str = "wss://realtime.streamelements.com/socket.io/?EIO=3&transport=websocket";
+
+pub fn authenticate_payload(jwt: &str) -> Value {
+    json!({ "method": "jwt", "token": jwt })
+}
+
+/// The tip in an `event` or `event:test` payload, if it is one.
+pub fn parse_event(name: &str, data: &Value) -> Option<Donation> {
+    let (tip, external_id) = match name {
+        "event" if data.get("type").and_then(|v| v.as_str()) == Some("tip") => {
+            let tip = data.get("data")?;
+            let id = tip.get("tipId").or_else(|| data.get("_id"))
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            (tip, id)
+        }
+        "event:test" if data.get("listener").and_then(|v| v.as_str()) == Some("tip-latest") => {
+            (data.get("event")?, None)
+        }
+        _ => return None,
+    };
+
+    let amount = tip.get("amount").and_then(parse_amount)?;
+    let name = ["displayName", "username", "name"].iter()
+        .find_map(|k| tip.get(*k).and_then(|v| v.as_str()))
+        .unwrap_or("Anonymous");
+    Some(
+        Donation::new(
+            DonationSource::StreamElements,
+            name,
+            amount,
+            tip.get("currency").and_then(|v| v.as_str()),
+        )
+        .with_external_id(external_id)
+        .with_message(tip.get("message").and_then(|v| v.as_str()).map(String::from)),
+    )
+}
@@ -0,0 +1,46 @@
+// File: maowbot-core/src/services/donations/streamlabs.rs
+//
+// Streamlabs socket API: connect with the socket API token from
+// Streamlabs → Settings → API Tokens; donations arrive as
+// `event` frames with `type: "donation"`.
+
+use serde_json::Value;
+use maowbot_common::models::donation::{parse_amount, Donation, DonationSource};
+
+const SOCKET_URL: &str = "wss://sockets.streamlabs.com/socket.io/";
+
+pub fn socket_url(token: &str) -> String {
+    format!("{SOCKET_URL}?token={}&EIO=3&transport=websocket", urlencoding::encode(token))
+}
+
+/// Donations in a Streamlabs `event` payload. `message` is usually an array,
+/// one entry per donation.
+pub fn parse_event(data: &Value) -> Vec<Donation> {
+    if data.get("type").and_then(|v| v.as_str()) != Some("donation") {
+        return vec![];
+    }
+    let entries = match data.get("message") {
+        Some(Value::Array(items)) => items.iter().collect::<Vec<_>>(),
+        Some(item @ Value::Object(_)) => vec![item],
+        _ => return vec![],
+    };
+    entries.into_iter()
+        .filter_map(|entry| {
+            let amount = entry.get("amount").and_then(parse_amount)?;
+            let name = entry.get("name").and_then(|v| v.as_str()).unwrap_or("Anonymous");
+            let external_id = ["donation_id", "_id", "id"].iter()
+                .find_map(|k| entry.get(*k))
+                .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()));
+            Some(
+                Donation::new(
+                    DonationSource::Streamlabs,
+                    name,
+                    amount,
+                    entry.get("currency").and_then(|v| v.as_str()),
+                )
+                .with_external_id(external_id)
+                .with_message(entry.get("message").and_then(|v| v.as_str()).map(String::from)),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,455 @@
+// File: maowbot-core/src/services/drip_pack.rs
+//
+// Exports OSC toggles and their redeems as shareable "drip pack" JSON, and
+// imports packs onto the current avatar's parameters.
+
+use std::collections::{HashMap, HashSet};
+use chrono::Utc;
+use tracing::{info, warn};
+use uuid::Uuid;
+use maowbot_common::models::osc_toggle::{
+    DripPack, DripPackToggle, OscParameterValue, OscTrigger, DRIP_PACK_FORMAT,
+};
+use maowbot_common::models::Redeem;
+use maowbot_common::traits::osc_toggle_traits::OscToggleRepository;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, RedeemRepository};
+use maowbot_osc::vrchat::VrchatAvatarConfig;
+use crate::platforms::twitch::requests::channel_points::CustomRewardBody;
+use crate::services::twitch::broadcaster_helix;
+use crate::tasks::redeem_sync::REDEEM_PLATFORM;
+use crate::Error;
+
+/// A parameter the avatar accepts over OSC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvatarParameter {
+    pub name: String,
+    /// "bool", "int" or "float"
+    pub parameter_type: String,
+}
+
+/// Parameters of an avatar config that can be written to, i.e. have an input address.
+pub fn avatar_parameters(config: &VrchatAvatarConfig) -> Vec<AvatarParameter> {
+    config.parameters.iter()
+        .filter_map(|p| p.input.as_ref().map(|input| AvatarParameter {
+            name: p.name.clone(),
+            parameter_type: input.param_type.to_lowercase(),
+        }))
+        .collect()
+}
+
+/// How a pack parameter was matched to an avatar parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    Exact,
+    IgnoringCase,
+    /// Same name once case, separators and folders are ignored, or one contains the other
+    Similar,
+    Unmatched,
+}
+
+impl std::fmt::Display for MatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MatchKind::Exact => "exact",
+            MatchKind::IgnoringCase => "ignoring_case",
+            MatchKind::Similar => "similar",
+            MatchKind::Unmatched => "unmatched",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The suggested avatar parameter for one pack parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterMatch {
+    pub pack_parameter: String,
+    pub parameter_type: String,
+    pub avatar_parameter: Option<String>,
+    pub avatar_type: Option<String>,
+    pub kind: MatchKind,
+}
+
+/// Lowercase alphanumerics of a parameter's last path segment, so
+/// "Clothing/Hoodie_On" and "hoodie-on" compare equal.
+fn normalized(name: &str) -> String {
+    name.rsplit('/').next().unwrap_or(name)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// How closely `have` matches the pack's `wanted` parameter, if at all.
+fn match_kind(wanted: &str, have: &str) -> Option<MatchKind> {
+    if have == wanted {
+        return Some(MatchKind::Exact);
+    }
+    if have.eq_ignore_ascii_case(wanted) {
+        return Some(MatchKind::IgnoringCase);
+    }
+    let (wanted, have) = (normalized(wanted), normalized(have));
+    let similar = (!wanted.is_empty() && wanted == have)
+        || (wanted.len() >= 3 && have.len() >= 3 && (have.contains(&wanted) || wanted.contains(&have)));
+    similar.then_some(MatchKind::Similar)
+}
+
+/// Suggests an avatar parameter for every distinct parameter in the pack:
+/// the closest match, then one of the pack's type, then the nearest length.
+pub fn match_parameters(pack: &DripPack, avatar: &[AvatarParameter]) -> Vec<ParameterMatch> {
+    let mut seen = HashSet::new();
+    pack.toggles.iter()
+        .filter(|t| seen.insert(t.parameter_name.clone()))
+        .map(|t| {
+            let best = avatar.iter()
+                .filter_map(|p| match_kind(&t.parameter_name, &p.name).map(|kind| (kind, p)))
+                .min_by_key(|(kind, p)| (
+                    *kind as u8,
+                    !p.parameter_type.eq_ignore_ascii_case(&t.parameter_type),
+                    p.name.len().abs_diff(t.parameter_name.len()),
+                ));
+            ParameterMatch {
+                pack_parameter: t.parameter_name.clone(),
+                parameter_type: t.parameter_type.clone(),
+                avatar_parameter: best.map(|(_, p)| p.name.clone()),
+                avatar_type: best.map(|(_, p)| p.parameter_type.clone()),
+                kind: best.map(|(k, _)| k).unwrap_or(MatchKind::Unmatched),
+            }
+        })
+        .collect()
+}
+
+/// Parses and checks a pack.
+pub fn parse_pack(json: &str) -> Result<DripPack, Error> {
+    let mut pack: DripPack = serde_json::from_str(json)
+        .map_err(|e| Error::Parse(format!("Invalid drip pack: {}", e)))?;
+    if pack.format > DRIP_PACK_FORMAT {
+        return Err(Error::Parse(format!(
+            "Drip pack format {} is newer than this bot supports ({})", pack.format, DRIP_PACK_FORMAT
+        )));
+    }
+    if pack.toggles.is_empty() {
+        return Err(Error::Parse("Drip pack has no toggles".into()));
+    }
+    for t in &mut pack.toggles {
+        t.parameter_type = t.parameter_type.to_lowercase();
+        if t.reward_name.trim().is_empty() || t.parameter_name.trim().is_empty() {
+            return Err(Error::Parse("Every toggle needs a reward_name and parameter_name".into()));
+        }
+        if t.cost < 1 {
+            return Err(Error::Parse(format!("'{}' must cost at least 1 point", t.reward_name)));
+        }
+        for value in [&t.on_value, &t.off_value] {
+            OscParameterValue::from_string(&t.parameter_type, value)
+                .map_err(|e| Error::Parse(format!("'{}': {}", t.reward_name, e)))?;
+        }
+    }
+    Ok(pack)
+}
+
+/// Bundles every OSC toggle with its redeem. Reward colors and images come from
+/// Twitch when the broadcaster is connected, and are left out otherwise.
+pub async fn export_pack(
+    name: &str,
+    avatar_name: Option<String>,
+    redeem_repo: &dyn RedeemRepository,
+    toggle_repo: &dyn OscToggleRepository,
+    credentials_repo: &(dyn CredentialsRepository + Send + Sync),
+) -> Result<DripPack, Error> {
+    let triggers = toggle_repo.get_all_triggers().await?;
+    if triggers.is_empty() {
+        return Err(Error::NotFound("No OSC toggles to export".into()));
+    }
+
+    let rewards = match broadcaster_helix(credentials_repo).await {
+        Ok(helix) => helix.client.get_custom_rewards(&helix.broadcaster_id, None, false).await
+            .unwrap_or_else(|e| {
+                warn!("[drip_pack] Exporting without reward colors: {}", e);
+                Vec::new()
+            }),
+        Err(_) => Vec::new(),
+    };
+
+    let mut toggles = Vec::with_capacity(triggers.len());
+    for trigger in triggers {
+        let Some(redeem) = redeem_repo.get_redeem_by_id(trigger.redeem_id).await? else {
+            warn!("[drip_pack] Skipping toggle {} whose redeem is gone", trigger.parameter_name);
+            continue;
+        };
+        let reward = rewards.iter().find(|r| r.id == redeem.reward_id);
+        toggles.push(DripPackToggle {
+            parameter_name: trigger.parameter_name,
+            parameter_type: trigger.parameter_type,
+            on_value: trigger.on_value,
+            off_value: trigger.off_value,
+            duration_seconds: trigger.duration_seconds,
+            cooldown_seconds: trigger.cooldown_seconds,
+            reward_name: redeem.reward_name,
+            cost: redeem.cost,
+            prompt: redeem.redeem_prompt_text.filter(|p| !p.is_empty()),
+            is_input_required: redeem.is_input_required,
+            background_color: reward.map(|r| r.background_color.clone()).filter(|c| !c.is_empty()),
+            icon_url: reward.and_then(|r| r.image.as_ref()).map(|i| i.url_4x.clone()),
+        });
+    }
+
+    Ok(DripPack {
+        format: DRIP_PACK_FORMAT,
+        name: name.to_string(),
+        author: None,
+        description: None,
+        avatar_name,
+        toggles,
+    })
+}
+
+/// What importing one toggle did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStatus {
+    Imported,
+    Skipped,
+    Failed,
+}
+
+impl std::fmt::Display for ImportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ImportStatus::Imported => "imported",
+            ImportStatus::Skipped => "skipped",
+            ImportStatus::Failed => "failed",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PackImportResult {
+    pub reward_name: String,
+    pub parameter_name: String,
+    pub status: ImportStatus,
+    pub redeem_id: Option<Uuid>,
+    pub trigger_id: Option<i32>,
+    /// The redeem was created rather than an existing one reused
+    pub created_redeem: bool,
+    pub detail: String,
+}
+
+/// Imports a pack. `mapping` renames pack parameters to avatar parameters; an
+/// empty target skips that parameter's toggles, and parameters missing from the
+/// map keep their pack name.
+///
+/// Redeems are reused by name. New ones are created on Twitch when the
+/// broadcaster is connected; otherwise they're saved locally and created by
+/// the next `redeem sync`.
+pub async fn import_pack(
+    pack: &DripPack,
+    mapping: &HashMap<String, String>,
+    redeem_repo: &dyn RedeemRepository,
+    toggle_repo: &dyn OscToggleRepository,
+    credentials_repo: &(dyn CredentialsRepository + Send + Sync),
+) -> Result<Vec<PackImportResult>, Error> {
+    let existing = redeem_repo.list_redeems(REDEEM_PLATFORM).await?;
+    let helix = broadcaster_helix(credentials_repo).await
+        .map_err(|e| warn!("[drip_pack] Twitch unavailable, new redeems will wait for a sync: {}", e))
+        .ok();
+
+    let mut results = Vec::with_capacity(pack.toggles.len());
+    for toggle in &pack.toggles {
+        let parameter_name = mapping.get(&toggle.parameter_name)
+            .cloned()
+            .unwrap_or_else(|| toggle.parameter_name.clone());
+        let mut result = PackImportResult {
+            reward_name: toggle.reward_name.clone(),
+            parameter_name: parameter_name.clone(),
+            status: ImportStatus::Skipped,
+            redeem_id: None,
+            trigger_id: None,
+            created_redeem: false,
+            detail: String::new(),
+        };
+        if parameter_name.trim().is_empty() {
+            result.detail = "not mapped to an avatar parameter".into();
+            results.push(result);
+            continue;
+        }
+
+        let redeem = match existing.iter().find(|r| r.reward_name.eq_ignore_ascii_case(&toggle.reward_name)) {
+            Some(rd) => rd.clone(),
+            None => match create_redeem(toggle, redeem_repo, helix.as_ref()).await {
+                Ok((rd, detail)) => {
+                    result.created_redeem = true;
+                    result.detail = detail;
+                    rd
+                }
+                Err(e) => {
+                    result.status = ImportStatus::Failed;
+                    result.detail = e.to_string();
+                    results.push(result);
+                    continue;
+                }
+            },
+        };
+        result.redeem_id = Some(redeem.redeem_id);
+
+        if let Some(trigger) = toggle_repo.get_trigger_by_redeem_id(redeem.redeem_id).await? {
+            result.trigger_id = Some(trigger.id);
+            result.detail = format!("'{}' already fires {}", redeem.reward_name, trigger.parameter_name);
+            results.push(result);
+            continue;
+        }
+
+        let trigger = OscTrigger {
+            id: 0,
+            redeem_id: redeem.redeem_id,
+            parameter_name,
+            parameter_type: toggle.parameter_type.clone(),
+            on_value: toggle.on_value.clone(),
+            off_value: toggle.off_value.clone(),
+            duration_seconds: toggle.duration_seconds,
+            cooldown_seconds: toggle.cooldown_seconds,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        match toggle_repo.create_trigger(trigger).await {
+            Ok(created) => {
+                result.status = ImportStatus::Imported;
+                result.trigger_id = Some(created.id);
+            }
+            Err(e) => {
+                result.status = ImportStatus::Failed;
+                result.detail = e.to_string();
+            }
+        }
+        results.push(result);
+    }
+
+    info!(
+        "[drip_pack] Imported '{}': {}/{} toggles",
+        pack.name,
+        results.iter().filter(|r| r.status == ImportStatus::Imported).count(),
+        results.len()
+    );
+    Ok(results)
+}
+
+async fn create_redeem(
+    toggle: &DripPackToggle,
+    redeem_repo: &dyn RedeemRepository,
+    helix: Option<&crate::services::twitch::BroadcasterHelix>,
+) -> Result<(Redeem, String), Error> {
+    let prompt = toggle.prompt.clone().filter(|p| !p.is_empty());
+    let (reward_id, detail) = match helix {
+        Some(h) => {
+            let body = CustomRewardBody {
+                title: Some(toggle.reward_name.clone()),
+                cost: Some(toggle.cost as u64),
+                prompt: prompt.clone(),
+                is_enabled: Some(true),
+                background_color: toggle.background_color.clone(),
+                is_user_input_required: Some(toggle.is_input_required),
+                ..Default::default()
+            };
+            let created = h.client.create_custom_reward(&h.broadcaster_id, &body).await?;
+            (created.id, "created on Twitch".to_string())
+        }
+        // Placeholder id; the next sync sees a managed redeem without a reward
+        None => (format!("pending-{}", Uuid::new_v4()), "waiting for `redeem sync to`".to_string()),
+    };
+
+    let now = Utc::now();
+    let rd = Redeem {
+        redeem_id: Uuid::new_v4(),
+        platform: REDEEM_PLATFORM.to_string(),
+        reward_id,
+        reward_name: toggle.reward_name.clone(),
+        cost: toggle.cost,
+        is_active: true,
+        dynamic_pricing: false,
+        active_offline: false,
+        is_managed: true,
+        plugin_name: None,
+        command_name: None,
+        created_at: now,
+        updated_at: now,
+        active_credential_id: None,
+        is_input_required: toggle.is_input_required,
+        redeem_prompt_text: prompt,
+        requires_approval: false,
+    };
+    redeem_repo.create_redeem(&rd).await?;
+    Ok((rd, detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toggle(parameter_name: &str, parameter_type: &str) -> DripPackToggle {
+        DripPackToggle {
+            parameter_name: parameter_name.to_string(),
+            parameter_type: parameter_type.to_string(),
+            on_value: "true".to_string(),
+            off_value: "false".to_string(),
+            duration_seconds: Some(30),
+            cooldown_seconds: 0,
+            reward_name: format!("Toggle {}", parameter_name),
+            cost: 100,
+            prompt: None,
+            is_input_required: false,
+            background_color: None,
+            icon_url: None,
+        }
+    }
+
+    fn param(name: &str, parameter_type: &str) -> AvatarParameter {
+        AvatarParameter { name: name.to_string(), parameter_type: parameter_type.to_string() }
+    }
+
+    #[test]
+    fn test_matches_pack_parameters_to_avatar() {
+        let pack = DripPack {
+            format: DRIP_PACK_FORMAT,
+            name: "test".into(),
+            author: None,
+            description: None,
+            avatar_name: None,
+            toggles: vec![
+                toggle("Hoodie", "bool"),
+                toggle("hat", "bool"),
+                toggle("Clothing/Glasses_On", "bool"),
+                toggle("Tail", "bool"),
+                toggle("Hoodie", "bool"),
+                toggle("Wings", "bool"),
+            ],
+        };
+        let avatar = vec![
+            param("Hoodie", "bool"),
+            param("Hat", "bool"),
+            param("glasses-on", "bool"),
+            param("TailWag", "float"),
+            param("TailToggle", "bool"),
+        ];
+        let matches = match_parameters(&pack, &avatar);
+        let summary: Vec<(&str, Option<&str>, MatchKind)> = matches.iter()
+            .map(|m| (m.pack_parameter.as_str(), m.avatar_parameter.as_deref(), m.kind))
+            .collect();
+        assert_eq!(summary, vec![
+            ("Hoodie", Some("Hoodie"), MatchKind::Exact),
+            ("hat", Some("Hat"), MatchKind::IgnoringCase),
+            ("Clothing/Glasses_On", Some("glasses-on"), MatchKind::Similar),
+            ("Tail", Some("TailToggle"), MatchKind::Similar),
+            ("Wings", None, MatchKind::Unmatched),
+        ]);
+    }
+
+    #[test]
+    fn test_rejects_bad_packs() {
+        assert!(parse_pack("{\"name\":\"x\",\"toggles\":[]}").is_err());
+        let mut t = toggle("Hat", "bool");
+        t.on_value = "yes".into();
+        let json = serde_json::json!({ "name": "x", "toggles": [t] }).to_string();
+        assert!(parse_pack(&json).is_err());
+        let json = serde_json::json!({ "name": "x", "toggles": [toggle("Hat", "bool")] }).to_string();
+        assert_eq!(parse_pack(&json).unwrap().format, DRIP_PACK_FORMAT);
+    }
+}
@@ -0,0 +1,288 @@
+// File: maowbot-core/src/services/emote_sets/mod.rs
+//
+// Keeps the 7TV, BTTV and FFZ emotes of Twitch channels cached: the channels
+// listed in `emotes.channels` (the broadcaster's by default) plus any channel
+// emote stats or a UI client asks about. Sets are refetched on an interval and
+// whenever 7TV's event API reports a change to a channel's active set.
+
+pub mod providers;
+mod seventv_events;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::eventbus::EventBus;
+use crate::services::emote_stats::emotes::{EmoteSet, KnownEmote};
+use crate::services::emote_stats::normalize_channel;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use self::providers::{ChannelFetch, EmoteProviderClient, SevenTvIds};
+
+/// Used when `emotes.refresh_minutes` is unset.
+const DEFAULT_REFRESH_MINUTES: i64 = 30;
+/// How often the refresh loop looks for stale channels.
+const REFRESH_CHECK_SECS: u64 = 60;
+
+/// A channel's third-party emotes as last fetched.
+#[derive(Debug, Clone)]
+pub struct ChannelEmotes {
+    pub channel: String,
+    /// The channel's own emotes
+    pub emotes: Vec<KnownEmote>,
+    /// Emotes every channel has
+    pub globals: Arc<Vec<KnownEmote>>,
+    pub fetched_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct ChannelSets {
+    /// The channel owner's Twitch user id
+    twitch_id: Option<String>,
+    emotes: Vec<KnownEmote>,
+    /// Globals and the channel's emotes by code
+    set: Arc<EmoteSet>,
+    seventv: Option<SevenTvIds>,
+    fetched_at: Option<DateTime<Utc>>,
+    /// Set before fetching so a failing API isn't retried on every message
+    attempted_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct SetsState {
+    channels: HashMap<String, ChannelSets>,
+    globals: Arc<Vec<KnownEmote>>,
+    globals_attempted_at: Option<Instant>,
+}
+
+pub struct EmoteSetService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    settings: Arc<SettingsRegistry>,
+    event_bus: Arc<EventBus>,
+    providers: EmoteProviderClient,
+    state: Mutex<SetsState>,
+}
+
+impl EmoteSetService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        settings: Arc<SettingsRegistry>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self {
+            credentials_repo,
+            settings,
+            event_bus,
+            providers: EmoteProviderClient::new(),
+            state: Mutex::new(SetsState::default()),
+        }
+    }
+
+    /// Refreshes stale channels every minute and follows 7TV set changes,
+    /// while `emotes.third_party` is on.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            let mut check = tokio::time::interval(StdDuration::from_secs(REFRESH_CHECK_SECS));
+            loop {
+                tokio::select! {
+                    _ = check.tick() => {
+                        if service.enabled() {
+                            service.refresh_stale().await;
+                        }
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[EmoteSets] refresh loop stopped");
+        });
+
+        tokio::spawn(seventv_events::run(self.clone()));
+    }
+
+    fn enabled(&self) -> bool {
+        self.settings.get_bool("emotes.third_party").unwrap_or(true)
+    }
+
+    fn refresh_interval(&self) -> StdDuration {
+        let minutes = self.settings.get_i64("emotes.refresh_minutes").unwrap_or(DEFAULT_REFRESH_MINUTES).max(1);
+        StdDuration::from_secs(minutes as u64 * 60)
+    }
+
+    fn stale(&self, attempted_at: Option<Instant>) -> bool {
+        attempted_at.is_none_or(|at| at.elapsed() >= self.refresh_interval())
+    }
+
+    /// The broadcaster's channel, used when a request names none.
+    pub async fn default_channel(&self) -> Result<String, Error> {
+        self.credentials_repo.get_broadcaster_credential(&Platform::Twitch).await?
+            .map(|cred| normalize_channel(&cred.user_name))
+            .ok_or_else(|| Error::NotFound("No Twitch broadcaster account; name a channel".into()))
+    }
+
+    /// `emotes.channels`, or the broadcaster's channel when that's blank.
+    async fn configured_channels(&self) -> Vec<String> {
+        let listed: Vec<String> = self.settings.get("emotes.channels")
+            .unwrap_or_default()
+            .split(',')
+            .map(normalize_channel)
+            .filter(|c| !c.is_empty())
+            .collect();
+        if !listed.is_empty() {
+            return listed;
+        }
+        self.default_channel().await.into_iter().collect()
+    }
+
+    /// Fetches the configured channels and every channel already cached whose
+    /// sets are older than `emotes.refresh_minutes`.
+    async fn refresh_stale(&self) {
+        let mut channels = self.configured_channels().await;
+        {
+            let state = self.state.lock();
+            for name in state.channels.keys() {
+                if !channels.contains(name) {
+                    channels.push(name.clone());
+                }
+            }
+            channels.retain(|c| self.stale(state.channels.get(c).and_then(|chan| chan.attempted_at)));
+        }
+        for channel in channels {
+            if let Err(e) = self.refresh_channel(&channel).await {
+                warn!("[EmoteSets] could not refresh #{}: {:?}", channel, e);
+            }
+        }
+    }
+
+    /// Globals and the channel's 7TV/BTTV/FFZ emotes by code, fetched first if
+    /// stale. `room_id` is the owner's Twitch id when the caller knows it (from
+    /// the IRC `room-id` tag), which saves a Helix lookup.
+    pub async fn emote_set(&self, channel: &str, room_id: Option<&str>) -> Arc<EmoteSet> {
+        let channel = normalize_channel(channel);
+        {
+            let mut state = self.state.lock();
+            let globals_stale = self.stale(state.globals_attempted_at);
+            let chan = state.channels.entry(channel.clone()).or_default();
+            if let Some(id) = room_id.filter(|id| chan.twitch_id.as_deref() != Some(*id)) {
+                chan.twitch_id = Some(id.to_string());
+                chan.attempted_at = None;
+            }
+            if !globals_stale && !self.stale(chan.attempted_at) {
+                return chan.set.clone();
+            }
+        }
+
+        if let Err(e) = self.refresh_channel(&channel).await {
+            debug!("[EmoteSets] #{} emotes unavailable: {:?}", channel, e);
+        }
+        self.state.lock().channels.get(&channel).map(|c| c.set.clone()).unwrap_or_default()
+    }
+
+    /// The channel's emotes as cached, fetching them if it has none yet.
+    pub async fn channel_emotes(&self, channel: &str) -> Result<ChannelEmotes, Error> {
+        let channel = normalize_channel(channel);
+        let fetched = self.state.lock().channels.get(&channel).is_some_and(|c| c.fetched_at.is_some());
+        if !fetched {
+            self.refresh_channel(&channel).await?;
+        }
+        Ok(self.snapshot(&channel))
+    }
+
+    /// Refetches the channel's emotes now.
+    pub async fn refresh(&self, channel: &str) -> Result<ChannelEmotes, Error> {
+        let channel = normalize_channel(channel);
+        self.refresh_channel(&channel).await?;
+        Ok(self.snapshot(&channel))
+    }
+
+    fn snapshot(&self, channel: &str) -> ChannelEmotes {
+        let state = self.state.lock();
+        let chan = state.channels.get(channel);
+        ChannelEmotes {
+            channel: channel.to_string(),
+            emotes: chan.map(|c| c.emotes.clone()).unwrap_or_default(),
+            globals: state.globals.clone(),
+            fetched_at: chan.and_then(|c| c.fetched_at),
+        }
+    }
+
+    /// Fetches the globals if stale and the channel's own emotes, returning
+    /// how many emotes the channel has.
+    async fn refresh_channel(&self, channel: &str) -> Result<usize, Error> {
+        let (twitch_id, fetch_globals) = {
+            let mut state = self.state.lock();
+            let fetch_globals = self.stale(state.globals_attempted_at);
+            if fetch_globals {
+                state.globals_attempted_at = Some(Instant::now());
+            }
+            let chan = state.channels.entry(channel.to_string()).or_default();
+            chan.attempted_at = Some(Instant::now());
+            (chan.twitch_id.clone(), fetch_globals)
+        };
+        if fetch_globals {
+            let globals = self.providers.global_emotes().await;
+            debug!("[EmoteSets] loaded {} global third-party emotes", globals.len());
+            self.state.lock().globals = Arc::new(globals);
+        }
+        let twitch_id = match twitch_id {
+            Some(id) => Ok(id),
+            None => self.resolve_twitch_id(channel).await,
+        };
+        // Without the owner's id the channel still gets the globals
+        let fetched = match &twitch_id {
+            Ok(id) => self.providers.channel_emotes(id).await,
+            Err(_) => ChannelFetch::default(),
+        };
+
+        let mut state = self.state.lock();
+        let globals = state.globals.clone();
+        let chan = state.channels.entry(channel.to_string()).or_default();
+        chan.set = Arc::new(EmoteSet::from_emotes(globals.iter().cloned().chain(fetched.emotes.iter().cloned())));
+        chan.emotes = fetched.emotes;
+        chan.seventv = fetched.seventv;
+        let id = twitch_id?;
+        chan.twitch_id = Some(id);
+        chan.fetched_at = Some(Utc::now());
+        debug!("[EmoteSets] #{} has {} third-party emotes", channel, chan.emotes.len());
+        Ok(chan.emotes.len())
+    }
+
+    /// The broadcaster's id for their own channel, otherwise a Helix lookup.
+    async fn resolve_twitch_id(&self, channel: &str) -> Result<String, Error> {
+        let helix = broadcaster_helix(&*self.credentials_repo).await?;
+        if helix.login.eq_ignore_ascii_case(channel) {
+            return Ok(helix.broadcaster_id);
+        }
+        helix.client.fetch_user_id(channel).await?
+            .ok_or_else(|| Error::NotFound(format!("No Twitch channel named '{}'", channel)))
+    }
+
+    /// 7TV ids of the cached channels that have a 7TV account.
+    fn seventv_watch(&self) -> Vec<SevenTvIds> {
+        self.state.lock().channels.values().filter_map(|c| c.seventv.clone()).collect()
+    }
+
+    /// Refetches the channel whose 7TV account or emote set is `object_id`.
+    async fn seventv_changed(&self, object_id: &str) {
+        let channel = self.state.lock().channels.iter()
+            .find(|(_, c)| c.seventv.as_ref().is_some_and(|ids| ids.user_id == object_id || ids.emote_set_id == object_id))
+            .map(|(name, _)| name.clone());
+        let Some(channel) = channel else { return };
+        match self.refresh_channel(&channel).await {
+            Ok(count) => info!("[EmoteSets] 7TV set of #{} changed, now {} emotes", channel, count),
+            Err(e) => warn!("[EmoteSets] could not refetch #{} after a 7TV change: {:?}", channel, e),
+        }
+    }
+}
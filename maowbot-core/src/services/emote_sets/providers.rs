@@ -0,0 +1,201 @@
+// File: maowbot-core/src/services/emote_sets/providers.rs
+//
+// Public, unauthenticated emote APIs of 7TV, BetterTTV and FrankerFaceZ.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::{Client as ReqwestClient, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::debug;
+
+use maowbot_common::models::emote_stats::EmoteProvider;
+use crate::Error;
+use crate::services::emote_stats::emotes::KnownEmote;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+struct SevenTvEmote {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SevenTvEmoteSet {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    emotes: Vec<SevenTvEmote>,
+}
+
+#[derive(Deserialize)]
+struct SevenTvAccount {
+    id: String,
+}
+
+/// A Twitch connection of a 7TV account.
+#[derive(Deserialize)]
+struct SevenTvUser {
+    emote_set: Option<SevenTvEmoteSet>,
+    user: Option<SevenTvAccount>,
+}
+
+#[derive(Deserialize)]
+struct BttvEmote {
+    id: String,
+    code: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BttvUser {
+    #[serde(default)]
+    channel_emotes: Vec<BttvEmote>,
+    #[serde(default)]
+    shared_emotes: Vec<BttvEmote>,
+}
+
+#[derive(Deserialize)]
+struct FfzEmote {
+    id: u64,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct FfzSet {
+    #[serde(default)]
+    emoticons: Vec<FfzEmote>,
+}
+
+#[derive(Deserialize)]
+struct FfzRoom {
+    #[serde(default)]
+    sets: HashMap<String, FfzSet>,
+}
+
+#[derive(Deserialize)]
+struct FfzGlobal {
+    #[serde(default)]
+    default_sets: Vec<u64>,
+    #[serde(default)]
+    sets: HashMap<String, FfzSet>,
+}
+
+fn seven_tv(emotes: Vec<SevenTvEmote>) -> impl Iterator<Item = KnownEmote> {
+    emotes.into_iter().map(|e| KnownEmote { provider: EmoteProvider::SevenTv, id: e.id, name: e.name })
+}
+
+fn bttv(emotes: Vec<BttvEmote>) -> impl Iterator<Item = KnownEmote> {
+    emotes.into_iter().map(|e| KnownEmote { provider: EmoteProvider::Bttv, id: e.id, name: e.code })
+}
+
+fn ffz(set: FfzSet) -> impl Iterator<Item = KnownEmote> {
+    set.emoticons.into_iter().map(|e| KnownEmote { provider: EmoteProvider::Ffz, id: e.id.to_string(), name: e.name })
+}
+
+/// The 7TV objects whose change events affect a channel's emotes: the account
+/// (which switches the active set) and the active set itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SevenTvIds {
+    pub user_id: String,
+    pub emote_set_id: String,
+}
+
+/// A channel's own emotes, and its 7TV ids if it has a 7TV account.
+#[derive(Debug, Default)]
+pub struct ChannelFetch {
+    pub emotes: Vec<KnownEmote>,
+    pub seventv: Option<SevenTvIds>,
+}
+
+/// Fetches emote sets. A provider that fails is logged and skipped, so one
+/// outage doesn't stop the others from being counted.
+pub struct EmoteProviderClient {
+    http: ReqwestClient,
+}
+
+impl EmoteProviderClient {
+    pub fn new() -> Self {
+        Self {
+            http: ReqwestClient::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `None` when the provider doesn't know the channel (404).
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<Option<T>, Error> {
+        let resp = self.http.get(url).send().await?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp.error_for_status()
+            .map_err(|e| Error::Platform(format!("Emote API request failed: {e}")))?;
+        Ok(Some(resp.json().await?))
+    }
+
+    /// Emotes every channel has: 7TV, BTTV and FFZ globals.
+    pub async fn global_emotes(&self) -> Vec<KnownEmote> {
+        let mut emotes = Vec::new();
+
+        match self.get_json::<SevenTvEmoteSet>("https://7tv.io/v3/emote-sets/global").await {
+            Ok(set) => emotes.extend(set.into_iter().flat_map(|s| seven_tv(s.emotes))),
+            Err(e) => debug!("[Emotes] 7TV globals unavailable: {:?}", e),
+        }
+        match self.get_json::<Vec<BttvEmote>>("https://api.betterttv.net/3/cached/emotes/global").await {
+            Ok(list) => emotes.extend(list.into_iter().flat_map(bttv)),
+            Err(e) => debug!("[Emotes] BTTV globals unavailable: {:?}", e),
+        }
+        match self.get_json::<FfzGlobal>("https://api.frankerfacez.com/v1/set/global").await {
+            Ok(Some(mut global)) => {
+                for set_id in global.default_sets {
+                    if let Some(set) = global.sets.remove(&set_id.to_string()) {
+                        emotes.extend(ffz(set));
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => debug!("[Emotes] FFZ globals unavailable: {:?}", e),
+        }
+        emotes
+    }
+
+    /// A channel's own 7TV, BTTV and FFZ emotes, by the owner's Twitch user id.
+    pub async fn channel_emotes(&self, twitch_user_id: &str) -> ChannelFetch {
+        let mut emotes = Vec::new();
+        let mut seventv = None;
+
+        match self.get_json::<SevenTvUser>(&format!("https://7tv.io/v3/users/twitch/{twitch_user_id}")).await {
+            Ok(Some(user)) => {
+                if let (Some(account), Some(set)) = (&user.user, &user.emote_set) {
+                    seventv = Some(SevenTvIds { user_id: account.id.clone(), emote_set_id: set.id.clone() });
+                }
+                emotes.extend(user.emote_set.into_iter().flat_map(|s| seven_tv(s.emotes)));
+            }
+            Ok(None) => {}
+            Err(e) => debug!("[Emotes] 7TV emotes of {} unavailable: {:?}", twitch_user_id, e),
+        }
+        match self.get_json::<BttvUser>(&format!("https://api.betterttv.net/3/cached/users/twitch/{twitch_user_id}")).await {
+            Ok(Some(user)) => {
+                emotes.extend(bttv(user.shared_emotes));
+                emotes.extend(bttv(user.channel_emotes));
+            }
+            Ok(None) => {}
+            Err(e) => debug!("[Emotes] BTTV emotes of {} unavailable: {:?}", twitch_user_id, e),
+        }
+        match self.get_json::<FfzRoom>(&format!("https://api.frankerfacez.com/v1/room/id/{twitch_user_id}")).await {
+            Ok(room) => emotes.extend(room.into_iter().flat_map(|r| r.sets.into_values().flat_map(ffz))),
+            Err(e) => debug!("[Emotes] FFZ emotes of {} unavailable: {:?}", twitch_user_id, e),
+        }
+        ChannelFetch { emotes, seventv }
+    }
+}
+
+impl Default for EmoteProviderClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,177 @@
+// File: maowbot-core/src/services/emote_sets/seventv_events.rs
+//
+// 7TV's event API (https://events.7tv.io/v3): subscribes to the active emote
+// set and the account of every cached channel, and refetches a channel when
+// either changes (emotes added, removed or renamed, or another set activated).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::time::{interval_at, Instant as TokioInstant};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::{debug, info, trace, warn};
+
+use crate::Error;
+use super::EmoteSetService;
+
+const EVENTS_URL: &str = "wss://events.7tv.io/v3";
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(300);
+/// How often newly cached channels are subscribed to.
+const WATCH_CHECK: Duration = Duration::from_secs(60);
+/// How long to wait before checking again while there's nothing to watch.
+const IDLE_DELAY: Duration = Duration::from_secs(60);
+
+const OP_DISPATCH: u64 = 0;
+const OP_HELLO: u64 = 1;
+const OP_RECONNECT: u64 = 4;
+const OP_ERROR: u64 = 6;
+const OP_END_OF_STREAM: u64 = 7;
+const OP_SUBSCRIBE: u64 = 35;
+
+#[derive(Debug, PartialEq)]
+enum Frame {
+    Hello,
+    /// An object we subscribed to changed
+    Changed(String),
+    /// The server is going away; connect again
+    Reconnect,
+    Error(String),
+    Other,
+}
+
+fn parse_frame(text: &str) -> Frame {
+    let Ok(value) = serde_json::from_str::<Value>(text) else { return Frame::Other };
+    let d = &value["d"];
+    match value["op"].as_u64() {
+        Some(OP_DISPATCH) => match d["body"]["id"].as_str() {
+            Some(id) => Frame::Changed(id.to_string()),
+            None => Frame::Other,
+        },
+        Some(OP_HELLO) => Frame::Hello,
+        Some(OP_RECONNECT) | Some(OP_END_OF_STREAM) => Frame::Reconnect,
+        Some(OP_ERROR) => Frame::Error(d["message"].as_str().unwrap_or("unknown error").to_string()),
+        _ => Frame::Other,
+    }
+}
+
+fn subscribe_message(kind: &str, object_id: &str) -> String {
+    json!({
+        "op": OP_SUBSCRIBE,
+        "d": { "type": kind, "condition": { "object_id": object_id } },
+    }).to_string()
+}
+
+/// Keeps the event API connected while third-party emotes are on and some
+/// cached channel has a 7TV account, backing off between attempts.
+pub(super) async fn run(service: Arc<EmoteSetService>) {
+    let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+    let mut delay = Duration::from_secs(1);
+    loop {
+        if !service.enabled() || service.seventv_watch().is_empty() {
+            tokio::select! {
+                _ = tokio::time::sleep(IDLE_DELAY) => continue,
+                _ = shutdown_rx.changed() => return,
+            }
+        }
+
+        let started = Instant::now();
+        tokio::select! {
+            res = connect_once(&service) => match res {
+                Ok(()) => debug!("[EmoteSets] 7TV event connection closed"),
+                Err(e) => warn!("[EmoteSets] 7TV event connection failed: {:?}", e),
+            },
+            _ = shutdown_rx.changed() => return,
+        }
+        if started.elapsed() > MAX_RECONNECT_DELAY {
+            delay = Duration::from_secs(1);
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown_rx.changed() => return,
+        }
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+async fn connect_once(service: &EmoteSetService) -> Result<(), Error> {
+    let (ws, _) = connect_async(EVENTS_URL)
+        .await
+        .map_err(|e| Error::Platform(format!("7TV event API connect error: {e}")))?;
+    info!("[EmoteSets] connected to the 7TV event API");
+    let (mut sink, mut stream) = ws.split();
+
+    // Subscriptions are only accepted after Hello
+    let mut ready = false;
+    let mut subscribed: HashSet<String> = HashSet::new();
+    let mut check = interval_at(TokioInstant::now() + WATCH_CHECK, WATCH_CHECK);
+
+    loop {
+        let subscribe = tokio::select! {
+            msg = stream.next() => {
+                let msg = match msg {
+                    Some(Ok(m)) => m,
+                    Some(Err(e)) => return Err(Error::Platform(format!("7TV event API error: {e}"))),
+                    None => return Ok(()),
+                };
+                if msg.is_close() {
+                    return Ok(());
+                }
+                let Message::Text(txt) = msg else { continue };
+                match parse_frame(&txt) {
+                    Frame::Hello => {
+                        ready = true;
+                        true
+                    }
+                    // A changed account may have switched to a set we don't follow yet
+                    Frame::Changed(object_id) => {
+                        service.seventv_changed(&object_id).await;
+                        true
+                    }
+                    Frame::Reconnect => return Ok(()),
+                    Frame::Error(message) => {
+                        warn!("[EmoteSets] 7TV event API error: {}", message);
+                        false
+                    }
+                    Frame::Other => {
+                        trace!("[EmoteSets] ignoring 7TV frame: {}", txt);
+                        false
+                    }
+                }
+            }
+            _ = check.tick() => true,
+        };
+
+        if subscribe && ready {
+            for ids in service.seventv_watch() {
+                for (kind, object_id) in [("emote_set.update", &ids.emote_set_id), ("user.update", &ids.user_id)] {
+                    if subscribed.insert(object_id.clone()) {
+                        sink.send(Message::text(subscribe_message(kind, object_id))).await
+                            .map_err(|e| Error::Platform(format!("7TV subscribe failed: {e}")))?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_frames() {
+        assert_eq!(parse_frame(r#"{"op":1,"d":{"heartbeat_interval":45000,"session_id":"abc"}}"#), Frame::Hello);
+        assert_eq!(
+            parse_frame(r#"{"op":0,"t":1700000000,"d":{"type":"emote_set.update","body":{"id":"60b3","pushed":[]}}}"#),
+            Frame::Changed("60b3".to_string())
+        );
+        assert_eq!(parse_frame(r#"{"op":7,"d":{"code":4000,"message":"bye"}}"#), Frame::Reconnect);
+        assert_eq!(parse_frame(r#"{"op":6,"d":{"message":"bad sub"}}"#), Frame::Error("bad sub".to_string()));
+        assert_eq!(parse_frame(r#"{"op":2,"d":{"count":3}}"#), Frame::Other);
+        assert_eq!(parse_frame("not json"), Frame::Other);
+    }
+}
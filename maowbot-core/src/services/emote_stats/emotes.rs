@@ -0,0 +1,116 @@
+// File: maowbot-core/src/services/emote_stats/emotes.rs
+//
+// Finds the emotes in a chat line: Twitch emotes from the IRC `emotes` tag,
+// 7TV/BTTV/FFZ emotes by matching words against the channel's emote sets.
+
+use std::collections::{HashMap, HashSet};
+
+use maowbot_common::models::emote_stats::EmoteProvider;
+
+/// An emote that can be recognized in chat.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KnownEmote {
+    pub provider: EmoteProvider,
+    pub id: String,
+    pub name: String,
+}
+
+/// Third-party emotes by code. Codes are case-sensitive.
+#[derive(Debug, Default)]
+pub struct EmoteSet {
+    by_name: HashMap<String, KnownEmote>,
+}
+
+impl EmoteSet {
+    /// Later emotes win over earlier ones with the same code, so pass globals
+    /// first and channel emotes last.
+    pub fn from_emotes(emotes: impl IntoIterator<Item = KnownEmote>) -> Self {
+        let mut by_name = HashMap::new();
+        for emote in emotes {
+            by_name.insert(emote.name.clone(), emote);
+        }
+        Self { by_name }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&KnownEmote> {
+        self.by_name.get(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+}
+
+/// Twitch emotes named by an `emotes` tag such as "25:0-4,12-16/1902:6-10",
+/// one entry per occurrence. Positions are character offsets into `text`.
+pub fn parse_twitch_emotes(tag: &str, text: &str) -> Vec<KnownEmote> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut found = Vec::new();
+    for entry in tag.split('/').filter(|e| !e.is_empty()) {
+        let Some((id, ranges)) = entry.split_once(':') else { continue };
+        for range in ranges.split(',') {
+            let Some((start, end)) = range.split_once('-') else { continue };
+            let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) else { continue };
+            if start > end || end >= chars.len() {
+                continue;
+            }
+            found.push(KnownEmote {
+                provider: EmoteProvider::Twitch,
+                id: id.to_string(),
+                name: chars[start..=end].iter().collect(),
+            });
+        }
+    }
+    found
+}
+
+/// Every emote occurrence in a chat line. Words already covered by the Twitch
+/// tag aren't matched again against `third_party`.
+pub fn find_emotes(text: &str, twitch_tag: Option<&str>, third_party: Option<&EmoteSet>) -> Vec<KnownEmote> {
+    let mut found = twitch_tag.map(|tag| parse_twitch_emotes(tag, text)).unwrap_or_default();
+    if let Some(set) = third_party.filter(|s| !s.is_empty()) {
+        let native: HashSet<String> = found.iter().map(|e| e.name.clone()).collect();
+        found.extend(
+            text.split_whitespace()
+                .filter(|word| !native.contains(*word))
+                .filter_map(|word| set.get(word).cloned()),
+        );
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emote(provider: EmoteProvider, id: &str, name: &str) -> KnownEmote {
+        KnownEmote { provider, id: id.to_string(), name: name.to_string() }
+    }
+
+    #[test]
+    fn test_parses_twitch_tag_by_character_offsets() {
+        let found = parse_twitch_emotes("25:7-11,19-23/1902:13-17", "héllo! Kappa Keepo Kappa");
+        let names: Vec<&str> = found.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Kappa", "Kappa", "Keepo"]);
+        assert!(parse_twitch_emotes("25:0-99", "Kappa").is_empty());
+    }
+
+    #[test]
+    fn test_channel_emotes_override_globals_and_twitch_wins() {
+        let set = EmoteSet::from_emotes([
+            emote(EmoteProvider::Bttv, "g1", "catJAM"),
+            emote(EmoteProvider::Ffz, "g2", "Kappa"),
+            emote(EmoteProvider::SevenTv, "c1", "catJAM"),
+        ]);
+        let found = find_emotes("catJAM Kappa catjam catJAM", Some("25:7-11"), Some(&set));
+        assert_eq!(found, vec![
+            emote(EmoteProvider::Twitch, "25", "Kappa"),
+            emote(EmoteProvider::SevenTv, "c1", "catJAM"),
+            emote(EmoteProvider::SevenTv, "c1", "catJAM"),
+        ]);
+    }
+}
@@ -0,0 +1,339 @@
+// File: maowbot-core/src/services/emote_stats/mod.rs
+//
+// Counts emote usage in Twitch chat: native emotes from the IRC `emotes` tag
+// plus 7TV, BTTV and FFZ emotes matched against each channel's emote sets
+// from the emote set service.
+// Recent usage is kept in per-minute buckets for rolling windows; totals per
+// day are flushed to the database for longer periods.
+
+pub mod emotes;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, warn};
+
+use maowbot_common::models::emote_stats::{EmoteDailyCount, EmoteProvider, EmoteUsage};
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, EmoteStatsRepository};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::services::emote_sets::EmoteSetService;
+use crate::services::message_dedupe::MessageDedupe;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use self::emotes::{find_emotes, KnownEmote};
+
+/// Minutes of per-minute buckets kept for rolling windows.
+pub const ROLLING_MINUTES: u32 = 60;
+/// Longest period the daily totals are queried over.
+pub const MAX_DAYS: u32 = 365;
+/// How often pending daily counts are written out.
+const FLUSH_INTERVAL_SECS: u64 = 60;
+
+/// The period a stats query covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsWindow {
+    /// The last N minutes, up to `ROLLING_MINUTES`
+    Minutes(u32),
+    /// Today and the N-1 days before it (UTC)
+    Days(u32),
+}
+
+impl StatsWindow {
+    /// Parses "15m", "1h", "7d" or "today".
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let s = s.trim().to_lowercase();
+        let invalid = || Error::Parse(format!("Invalid window '{}', expected e.g. 15m, 1h or 7d", s));
+        let number = |n: &str| n.parse::<u32>().map_err(|_| invalid());
+        let window = if s == "today" {
+            StatsWindow::Days(1)
+        } else if let Some(n) = s.strip_suffix('m') {
+            StatsWindow::Minutes(number(n)?)
+        } else if let Some(n) = s.strip_suffix('h') {
+            StatsWindow::Minutes(number(n)?.saturating_mul(60))
+        } else if let Some(n) = s.strip_suffix('d') {
+            StatsWindow::Days(number(n)?)
+        } else {
+            return Err(invalid());
+        };
+        window.validate()
+    }
+
+    pub fn validate(self) -> Result<Self, Error> {
+        match self {
+            StatsWindow::Minutes(m) if m == 0 || m > ROLLING_MINUTES => Err(Error::Parse(format!(
+                "Rolling windows go up to {} minutes; use days for longer periods", ROLLING_MINUTES
+            ))),
+            StatsWindow::Days(d) if d == 0 || d > MAX_DAYS => Err(Error::Parse(format!(
+                "Windows go up to {} days", MAX_DAYS
+            ))),
+            other => Ok(other),
+        }
+    }
+
+    /// e.g. "the last 15 minutes", "today", "the last 7 days".
+    pub fn describe(self) -> String {
+        match self {
+            StatsWindow::Minutes(60) => "the last hour".to_string(),
+            StatsWindow::Minutes(m) => format!("the last {} minutes", m),
+            StatsWindow::Days(1) => "today".to_string(),
+            StatsWindow::Days(d) => format!("the last {} days", d),
+        }
+    }
+}
+
+type EmoteKey = (EmoteProvider, String);
+
+/// Uses per emote, with the code last seen for it.
+type Counts = HashMap<EmoteKey, (String, i64)>;
+
+fn add_count(counts: &mut Counts, key: EmoteKey, name: &str, uses: i64) {
+    let entry = counts.entry(key).or_insert_with(|| (name.to_string(), 0));
+    entry.0 = name.to_string();
+    entry.1 += uses;
+}
+
+#[derive(Default)]
+struct ChannelState {
+    /// Oldest first
+    minutes: VecDeque<(DateTime<Utc>, Counts)>,
+}
+
+#[derive(Default)]
+struct StatsState {
+    channels: HashMap<String, ChannelState>,
+    /// Not yet written daily counts, by channel and day
+    pending: HashMap<(String, NaiveDate), Counts>,
+}
+
+pub struct EmoteStatsService {
+    repo: Arc<dyn EmoteStatsRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    emote_sets: Arc<EmoteSetService>,
+    state: Mutex<StatsState>,
+    seen: MessageDedupe,
+}
+
+pub(crate) fn normalize_channel(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+fn sorted_usage(channel: &str, counts: Counts, limit: usize) -> Vec<EmoteUsage> {
+    let mut usage: Vec<EmoteUsage> = counts.into_iter()
+        .map(|((provider, emote_id), (emote_name, uses))| EmoteUsage {
+            channel: channel.to_string(),
+            provider,
+            emote_id,
+            emote_name,
+            uses,
+        })
+        .collect();
+    usage.sort_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.emote_name.cmp(&b.emote_name)));
+    usage.truncate(limit);
+    usage
+}
+
+impl EmoteStatsService {
+    pub fn new(
+        repo: Arc<dyn EmoteStatsRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        emote_sets: Arc<EmoteSetService>,
+    ) -> Self {
+        Self {
+            repo,
+            credentials_repo,
+            event_bus,
+            settings,
+            emote_sets,
+            state: Mutex::new(StatsState::default()),
+            seen: MessageDedupe::default(),
+        }
+    }
+
+    /// Counts emotes in every Twitch chat line and flushes daily totals every
+    /// minute and on shutdown.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            let mut flush = tokio::time::interval(StdDuration::from_secs(FLUSH_INTERVAL_SECS));
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    _ = flush.tick() => service.flush().await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            service.flush().await;
+            debug!("[Emotes] event loop stopped");
+        });
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, text, timestamp, metadata, .. } = event else { return };
+        if platform != "twitch-irc" || !self.settings.get_bool("emotes.tracking_enabled").unwrap_or(true) {
+            return;
+        }
+        let field = |key: &str| metadata.get(key)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string);
+        if let Some(message_id) = field("message_id") {
+            if !self.seen.first_sighting(&message_id) {
+                return;
+            }
+        }
+        let channel = normalize_channel(&channel);
+
+        let third_party = if self.settings.get_bool("emotes.third_party").unwrap_or(true) {
+            Some(self.emote_sets.emote_set(&channel, field("room_id").as_deref()).await)
+        } else {
+            None
+        };
+        let found = find_emotes(&text, field("emotes").as_deref(), third_party.as_deref());
+        if !found.is_empty() {
+            self.record(&channel, timestamp, &found);
+        }
+    }
+
+    fn record(&self, channel: &str, at: DateTime<Utc>, found: &[KnownEmote]) {
+        let minute = at.duration_trunc(Duration::minutes(1)).unwrap_or(at);
+        let oldest = minute - Duration::minutes(ROLLING_MINUTES as i64);
+        let mut state = self.state.lock();
+
+        let chan = state.channels.entry(channel.to_string()).or_default();
+        while chan.minutes.front().is_some_and(|(m, _)| *m <= oldest) {
+            chan.minutes.pop_front();
+        }
+        if chan.minutes.back().is_none_or(|(m, _)| *m < minute) {
+            chan.minutes.push_back((minute, Counts::new()));
+        }
+        // A late message lands in the newest bucket rather than being dropped
+        let (_, bucket) = chan.minutes.back_mut().expect("bucket was just pushed");
+        for emote in found {
+            add_count(bucket, (emote.provider, emote.id.clone()), &emote.name, 1);
+        }
+
+        let pending = state.pending.entry((channel.to_string(), at.date_naive())).or_default();
+        for emote in found {
+            add_count(pending, (emote.provider, emote.id.clone()), &emote.name, 1);
+        }
+    }
+
+    /// Writes the pending daily counts; on failure they're kept for next time.
+    async fn flush(&self) {
+        let pending = std::mem::take(&mut self.state.lock().pending);
+        if pending.is_empty() {
+            return;
+        }
+        let counts: Vec<EmoteDailyCount> = pending.iter()
+            .flat_map(|((channel, day), counts)| counts.iter().map(|((provider, emote_id), (emote_name, uses))| {
+                EmoteDailyCount {
+                    day: *day,
+                    usage: EmoteUsage {
+                        channel: channel.clone(),
+                        provider: *provider,
+                        emote_id: emote_id.clone(),
+                        emote_name: emote_name.clone(),
+                        uses: *uses,
+                    },
+                }
+            }))
+            .collect();
+
+        if let Err(e) = self.repo.add_daily_counts(&counts).await {
+            warn!("[Emotes] could not save {} daily counts: {:?}", counts.len(), e);
+            let mut state = self.state.lock();
+            for (key, counts) in pending {
+                let merged = state.pending.entry(key).or_default();
+                for (emote, (name, uses)) in counts {
+                    add_count(merged, emote, &name, uses);
+                }
+            }
+        }
+    }
+
+    /// The broadcaster's channel, used when a query names none.
+    pub async fn default_channel(&self) -> Result<String, Error> {
+        self.credentials_repo.get_broadcaster_credential(&Platform::Twitch).await?
+            .map(|cred| normalize_channel(&cred.user_name))
+            .ok_or_else(|| Error::NotFound("No Twitch broadcaster account; name a channel".into()))
+    }
+
+    /// The most used emotes of a channel over `window`, most used first.
+    pub async fn top_emotes(&self, channel: &str, window: StatsWindow, limit: usize) -> Result<Vec<EmoteUsage>, Error> {
+        let window = window.validate()?;
+        let channel = normalize_channel(channel);
+        let now = Utc::now();
+
+        match window {
+            StatsWindow::Minutes(m) => {
+                // The current, partly filled minute counts as one of the m
+                let current = now.duration_trunc(Duration::minutes(1)).unwrap_or(now);
+                let since = current - Duration::minutes(m as i64 - 1);
+                let mut counts = Counts::new();
+                let state = self.state.lock();
+                if let Some(chan) = state.channels.get(&channel) {
+                    for (_, bucket) in chan.minutes.iter().filter(|(minute, _)| *minute >= since) {
+                        for (key, (name, uses)) in bucket {
+                            add_count(&mut counts, key.clone(), name, *uses);
+                        }
+                    }
+                }
+                Ok(sorted_usage(&channel, counts, limit))
+            }
+            StatsWindow::Days(d) => {
+                let since = now.date_naive() - Duration::days(d as i64 - 1);
+                let unsaved: Counts = {
+                    let state = self.state.lock();
+                    let mut counts = Counts::new();
+                    for ((_, _), day_counts) in state.pending.iter().filter(|((c, day), _)| *c == channel && *day >= since) {
+                        for (key, (name, uses)) in day_counts {
+                            add_count(&mut counts, key.clone(), name, *uses);
+                        }
+                    }
+                    counts
+                };
+
+                // Ask for enough rows that unsaved counts can't push a stored emote out unseen
+                let stored = self.repo.top_emotes(&channel, since, (limit + unsaved.len()) as i64).await?;
+                let mut counts = unsaved;
+                for usage in stored {
+                    add_count(&mut counts, (usage.provider, usage.emote_id), &usage.emote_name, usage.uses);
+                }
+                Ok(sorted_usage(&channel, counts, limit))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_windows() {
+        assert_eq!(StatsWindow::parse("15m").unwrap(), StatsWindow::Minutes(15));
+        assert_eq!(StatsWindow::parse("1H").unwrap(), StatsWindow::Minutes(60));
+        assert_eq!(StatsWindow::parse("7d").unwrap(), StatsWindow::Days(7));
+        assert_eq!(StatsWindow::parse("today").unwrap(), StatsWindow::Days(1));
+        assert!(StatsWindow::parse("2h").is_err());
+        assert!(StatsWindow::parse("0d").is_err());
+        assert!(StatsWindow::parse("soon").is_err());
+    }
+}
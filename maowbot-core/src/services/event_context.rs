@@ -5,8 +5,7 @@ use crate::services::RedeemService;
 use crate::services::message_service::MessageService;
 use crate::services::MessageSender;
 use crate::services::osc_toggle_service::OscToggleService;
-use crate::repositories::postgres::discord::PostgresDiscordRepository;
-use maowbot_common::traits::repository_traits::{BotConfigRepository, CredentialsRepository};
+use maowbot_common::traits::repository_traits::{BotConfigRepository, CredentialsRepository, DiscordRepository};
 
 /// EventContext encapsulates all services that event handlers might need.
 /// This allows us to pass a single object to handlers instead of many parameters,
@@ -20,7 +19,7 @@ pub struct EventContext {
     pub message_sender: Arc<MessageSender>,
     pub osc_toggle_service: Arc<OscToggleService>,
     pub bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
-    pub discord_repo: Arc<PostgresDiscordRepository>,
+    pub discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
     pub credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
 }
 
@@ -33,7 +32,7 @@ impl EventContext {
         message_sender: Arc<MessageSender>,
         osc_toggle_service: Arc<OscToggleService>,
         bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
-        discord_repo: Arc<PostgresDiscordRepository>,
+        discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
         credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
     ) -> Self {
         Self {
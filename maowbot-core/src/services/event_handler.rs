@@ -77,6 +77,7 @@ pub trait EventHandlerExt: EventHandler {
                 self.platforms().contains(&Platform::Twitch) || 
                 self.platforms().contains(&Platform::TwitchEventSub)
             }
+            BotEvent::Kick(_) => self.platforms().contains(&Platform::Kick),
             _ => false,
         }
     }
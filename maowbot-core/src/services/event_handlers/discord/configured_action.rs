@@ -1,7 +1,6 @@
 use async_trait::async_trait;
-use tracing::{debug, info, error};
+use tracing::debug;
 use maowbot_common::models::platform::Platform;
-use maowbot_common::models::discord::{DiscordEmbed, DiscordEmbedField, DiscordColor};
 
 use crate::Error;
 use crate::eventbus::BotEvent;
@@ -12,6 +11,12 @@ use crate::services::event_handler::EventHandler;
 /// Similar to how Twitch events can trigger Discord notifications
 pub struct DiscordConfiguredActionHandler;
 
+impl Default for DiscordConfiguredActionHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DiscordConfiguredActionHandler {
     pub fn new() -> Self {
         Self
@@ -100,74 +105,16 @@ impl DiscordConfiguredActionHandler {
 /// Handler for Discord member join/leave events with embed notifications
 pub struct MemberEventNotificationHandler;
 
+impl Default for MemberEventNotificationHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemberEventNotificationHandler {
     pub fn new() -> Self {
         Self
     }
-    
-    async fn send_member_notification(
-        &self,
-        ctx: &EventContext,
-        event_type: &str,
-        guild_id: &str,
-        user_id: &str,
-        username: &str,
-    ) -> Result<(), Error> {
-        // Check for configured notification channel
-        if let Some(config) = ctx.discord_repo.get_event_config_by_name(event_type).await? {
-            let mut embed = DiscordEmbed::new();
-            
-            match event_type {
-                "member.join" => {
-                    embed.title = Some(format!("Welcome {}!", username));
-                    embed.description = Some(format!("<@{}> has joined the server!", user_id));
-                    embed.color = Some(DiscordColor::GREEN);
-                    embed.timestamp = Some(chrono::Utc::now());
-                    
-                    embed.fields.push(DiscordEmbedField {
-                        name: "User ID".to_string(),
-                        value: user_id.to_string(),
-                        inline: true,
-                    });
-                }
-                "member.leave" => {
-                    embed.title = Some(format!("Goodbye {}", username));
-                    embed.description = Some(format!("<@{}> has left the server", user_id));
-                    embed.color = Some(DiscordColor::RED);
-                    embed.timestamp = Some(chrono::Utc::now());
-                }
-                _ => return Ok(()),
-            }
-            
-            // Send embed to configured channel
-            let account_name = if let Some(cred_id) = config.respond_with_credential {
-                if let Some(cred) = ctx.credentials_repo.get_credential_by_id(cred_id).await? {
-                    cred.user_name
-                } else {
-                    "bot".to_string()
-                }
-            } else {
-                "bot".to_string()
-            };
-            
-            info!(
-                "MemberEventNotificationHandler: Sending {} notification for {} to channel {}",
-                event_type, username, config.channel_id
-            );
-            
-            ctx.platform_manager
-                .send_discord_embed(
-                    &account_name,
-                    &config.guild_id,
-                    &config.channel_id,
-                    &embed,
-                    None,
-                )
-                .await?;
-        }
-        
-        Ok(())
-    }
 }
 
 #[async_trait]
@@ -184,7 +131,7 @@ impl EventHandler for MemberEventNotificationHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         // This would handle member events when added to BotEvent
         debug!("MemberEventNotificationHandler: Would send member event notification");
         Ok(false)
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use tracing::{debug, info, error};
+use tracing::{debug, info};
 use maowbot_common::models::platform::Platform;
 
 use crate::Error;
@@ -10,6 +10,12 @@ use crate::services::event_handler::EventHandler;
 /// Handler for Discord interaction events (slash commands, buttons, etc.)
 pub struct InteractionHandler;
 
+impl Default for InteractionHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl InteractionHandler {
     pub fn new() -> Self {
         Self
@@ -30,10 +36,10 @@ impl EventHandler for InteractionHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
-        // Note: Currently, slash commands are handled directly in the Discord runtime
-        // via the slashcommands module. This handler is a placeholder for when we
-        // create a proper DiscordEvent enum in the BotEvent system.
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
+        // Note: Slash commands are acknowledged in the Discord runtime and run by
+        // the command service like chat commands. This handler is a placeholder
+        // for when we create a proper DiscordEvent enum in the BotEvent system.
         
         debug!("InteractionHandler: Would handle interaction event");
         Ok(false)
@@ -58,11 +64,11 @@ impl SlashCommandHandler {
     
     pub async fn handle_command(
         &self,
-        ctx: &EventContext,
+        _ctx: &EventContext,
         guild_id: Option<&str>,
-        channel_id: &str,
+        _channel_id: &str,
         user_id: &str,
-        options: &[(&str, &str)],
+        _options: &[(&str, &str)],
     ) -> Result<(), Error> {
         match self.command_name.as_str() {
             "ping" => {
@@ -106,7 +112,7 @@ impl EventHandler for SlashCommandHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         // This would handle specific slash commands when we add them to BotEvent
         debug!("SlashCommandHandler: Would handle slash command: {}", self.command_name);
         Ok(false)
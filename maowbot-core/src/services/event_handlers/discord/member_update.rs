@@ -10,6 +10,12 @@ use crate::services::event_handler::EventHandler;
 /// Handler for Discord member update events
 pub struct MemberUpdateHandler;
 
+impl Default for MemberUpdateHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemberUpdateHandler {
     pub fn new() -> Self {
         Self
@@ -30,7 +36,7 @@ impl EventHandler for MemberUpdateHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         // This handler would process member updates such as:
         // - Role changes
         // - Nickname changes
@@ -47,6 +53,8 @@ impl EventHandler for MemberUpdateHandler {
 
 /// Handler for welcome messages when members join
 pub struct WelcomeMessageHandler {
+    // Read once the welcome message is actually sent
+    #[allow(dead_code)]
     welcome_channel_name: String,
 }
 
@@ -59,7 +67,7 @@ impl WelcomeMessageHandler {
     
     pub async fn send_welcome_message(
         &self,
-        ctx: &EventContext,
+        _ctx: &EventContext,
         guild_id: &str,
         user_id: &str,
         username: &str,
@@ -68,7 +76,7 @@ impl WelcomeMessageHandler {
         // This is a simplified example - in practice, you'd query Discord API
         // or use cached channel data
         
-        let welcome_message = format!(
+        let _welcome_message = format!(
             "Welcome to the server, <@{}>! 🎉\n\nPlease read the rules and enjoy your stay!",
             user_id
         );
@@ -102,7 +110,7 @@ impl EventHandler for WelcomeMessageHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         // This would handle member join events when we add them to BotEvent
         debug!("WelcomeMessageHandler: Would send welcome message");
         Ok(false)
@@ -5,11 +5,17 @@ use maowbot_common::models::platform::Platform;
 use crate::Error;
 use crate::eventbus::BotEvent;
 use crate::services::event_context::EventContext;
-use crate::services::event_handler::{EventHandler, TypedEventHandler};
+use crate::services::event_handler::EventHandler;
 
 /// Handler for Discord message events
 pub struct DiscordMessageHandler;
 
+impl Default for DiscordMessageHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DiscordMessageHandler {
     pub fn new() -> Self {
         Self
@@ -30,9 +36,9 @@ impl EventHandler for DiscordMessageHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         match event {
-            BotEvent::ChatMessage { platform, channel, user, text, timestamp, metadata: _ } => {
+            BotEvent::ChatMessage { platform, channel, user, text, timestamp: _, metadata: _ } => {
                 if platform == "discord" {
                     // Process Discord message
                     debug!("DiscordMessageHandler: Processing message from {} in {}: {}", user, channel, text);
@@ -102,7 +108,7 @@ impl EventHandler for DiscordCommandHandler {
                     }
                     
                     let command = parts[0];
-                    let args = &parts[1..];
+                    let _args = &parts[1..];
                     
                     // Handle Discord-specific commands
                     match command {
@@ -10,6 +10,12 @@ use crate::services::event_handler::EventHandler;
 /// Handler for Discord ready events (bot startup)
 pub struct ReadyHandler;
 
+impl Default for ReadyHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ReadyHandler {
     pub fn new() -> Self {
         Self
@@ -30,7 +36,7 @@ impl EventHandler for ReadyHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         // This handler would process the ready event which indicates
         // the bot has successfully connected to Discord
         
@@ -66,7 +72,7 @@ impl BotStatusHandler {
     
     pub async fn set_bot_status(
         &self,
-        ctx: &EventContext,
+        _ctx: &EventContext,
         bot_id: &str,
         bot_name: &str,
     ) -> Result<(), Error> {
@@ -96,7 +102,7 @@ impl EventHandler for BotStatusHandler {
         vec![Platform::Discord]
     }
 
-    async fn handle(&self, event: &BotEvent, ctx: &EventContext) -> Result<bool, Error> {
+    async fn handle(&self, _event: &BotEvent, _ctx: &EventContext) -> Result<bool, Error> {
         // This would handle setting bot status when ready event is received
         debug!("BotStatusHandler: Would set bot status on ready");
         Ok(false)
@@ -1,7 +1,6 @@
 pub mod twitch;
 pub mod discord;
 
-use std::sync::Arc;
 use crate::services::event_registry::EventHandlerRegistry;
 use crate::Error;
 
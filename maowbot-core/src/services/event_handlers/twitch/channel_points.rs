@@ -12,6 +12,12 @@ use crate::services::event_handler::{EventHandler, TypedEventHandler};
 /// Handler for Twitch channel points redemption events
 pub struct ChannelPointsRedemptionHandler;
 
+impl Default for ChannelPointsRedemptionHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ChannelPointsRedemptionHandler {
     pub fn new() -> Self {
         Self
@@ -56,7 +62,7 @@ impl TypedEventHandler<ChannelPointsCustomRewardRedemption> for ChannelPointsRed
         let redeem_id = &evt.reward.id;
         let user_id = &evt.user_id;
         let user_display = &evt.user_name;
-        let user_input = &evt.user_input;
+        let _user_input = &evt.user_input;
 
         info!(
             "ChannelPointsRedemptionHandler: Channel points redemption - reward: '{}' ({}), user: {} ({})",
@@ -68,8 +74,11 @@ impl TypedEventHandler<ChannelPointsCustomRewardRedemption> for ChannelPointsRed
             .get_or_create_user(&platform.to_string(), user_id, Some(user_display))
             .await?;
 
-        // Check if this redeem is in our database
-        match ctx.redeem_service.redeem_repo.get_redeem_by_reward_id(&platform.to_string(), redeem_id).await {
+        // Check if this redeem is in the database, in the broadcaster's workspace
+        let redeems = ctx.redeem_service
+            .workspace_repos(&platform.to_string(), &evt.broadcaster_user_login)
+            .redeems;
+        match redeems.get_redeem_by_reward_id(&platform.to_string(), redeem_id).await {
             Ok(Some(db_redeem)) => {
                 info!(
                     "ChannelPointsRedemptionHandler: Found redeem in database: {} (active: {})",
@@ -93,7 +102,7 @@ impl TypedEventHandler<ChannelPointsCustomRewardRedemption> for ChannelPointsRed
                             id: evt.reward.id.clone(),
                             title: evt.reward.title.clone(),
                             prompt: evt.reward.prompt.clone(),
-                            cost: evt.reward.cost as u64,
+                            cost: evt.reward.cost,
                         },
                     };
                     
@@ -103,7 +112,7 @@ impl TypedEventHandler<ChannelPointsCustomRewardRedemption> for ChannelPointsRed
                             &platform.to_string(),
                             redeem_id,
                             user.user_id,
-                            "twitch", // channel name
+                            &evt.broadcaster_user_login,
                             &redemption
                         )
                         .await
@@ -12,6 +12,12 @@ use crate::services::event_handler::{EventHandler, TypedEventHandler};
 /// Handler for Twitch stream.offline events
 pub struct StreamOfflineHandler;
 
+impl Default for StreamOfflineHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StreamOfflineHandler {
     pub fn new() -> Self {
         Self
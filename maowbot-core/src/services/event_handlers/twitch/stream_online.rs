@@ -14,6 +14,12 @@ use crate::tasks::redeem_sync;
 /// Handler for Twitch stream.online events
 pub struct StreamOnlineHandler;
 
+impl Default for StreamOnlineHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StreamOnlineHandler {
     pub fn new() -> Self {
         Self
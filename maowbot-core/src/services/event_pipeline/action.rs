@@ -1,9 +1,9 @@
 use async_trait::async_trait;
 use std::sync::Arc;
-use std::any::Any;
 use crate::Error;
 use crate::eventbus::BotEvent;
 use crate::services::event_context::EventContext;
+use crate::services::event_pipeline::variables::event_variables;
 
 /// Result of executing an action
 #[derive(Debug, Clone)]
@@ -14,25 +14,42 @@ pub enum ActionResult {
     Error(String),
 }
 
+/// What the pipeline does once the current action finishes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PipelineFlow {
+    /// Run the next action
+    #[default]
+    Continue,
+    /// Skip the remaining actions; the run still counts as a success
+    Stop,
+    /// Jump ahead to the first action whose order is at least this
+    GoTo(i32),
+}
+
 /// Context passed between actions in a pipeline
 pub struct ActionContext {
     /// The original event
     pub event: BotEvent,
     /// Shared event context with services
     pub context: Arc<EventContext>,
-    /// Data shared between actions in the pipeline
+    /// Data shared between actions in the pipeline, starting with the
+    /// event's variables
     pub shared_data: std::collections::HashMap<String, serde_json::Value>,
     /// Execution ID for tracking
     pub execution_id: uuid::Uuid,
+    /// Set by `stop` and `branch` actions
+    pub flow: PipelineFlow,
 }
 
 impl ActionContext {
     pub fn new(event: BotEvent, context: Arc<EventContext>) -> Self {
+        let shared_data = event_variables(&event).into_iter().collect();
         Self {
             event,
             context,
-            shared_data: std::collections::HashMap::new(),
+            shared_data,
             execution_id: uuid::Uuid::new_v4(),
+            flow: PipelineFlow::Continue,
         }
     }
 
@@ -45,6 +62,16 @@ impl ActionContext {
     pub fn get_data(&self, key: &str) -> Option<&serde_json::Value> {
         self.shared_data.get(key)
     }
+
+    /// End the pipeline after this action
+    pub fn stop(&mut self) {
+        self.flow = PipelineFlow::Stop;
+    }
+
+    /// Continue at the first action with `action_order >= order`
+    pub fn go_to(&mut self, order: i32) {
+        self.flow = PipelineFlow::GoTo(order);
+    }
 }
 
 /// Trait for pipeline actions
@@ -57,7 +84,7 @@ pub trait EventAction: Send + Sync {
     fn name(&self) -> &str;
     
     /// Configure the action from JSON configuration
-    fn configure(&mut self, config: serde_json::Value) -> Result<(), Error> {
+    fn configure(&mut self, _config: serde_json::Value) -> Result<(), Error> {
         // Default implementation does nothing
         Ok(())
     }
@@ -130,12 +157,9 @@ impl DiscordMessageAction {
         // Simple template replacement - could be enhanced with proper templating
         let mut message = self.message_template.clone();
         
-        match &context.event {
-            BotEvent::ChatMessage { user, text, .. } => {
-                message = message.replace("{user}", user);
-                message = message.replace("{message}", text);
-            }
-            _ => {}
+        if let BotEvent::ChatMessage { user, text, .. } = &context.event {
+            message = message.replace("{user}", user);
+            message = message.replace("{message}", text);
         }
         
         message
@@ -195,7 +219,7 @@ impl EventAction for OSCTriggerAction {
         "Trigger OSC Parameter"
     }
 
-    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+    async fn execute(&self, _context: &mut ActionContext) -> Result<ActionResult, Error> {
         // This would use the OSC toggle service to trigger the parameter
         tracing::info!(
             "OSCTriggerAction: Would trigger {} = {} for {:?}ms",
@@ -244,7 +268,7 @@ impl EventAction for PluginAction {
         "Execute Plugin Function"
     }
 
-    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+    async fn execute(&self, _context: &mut ActionContext) -> Result<ActionResult, Error> {
         tracing::info!(
             "PluginAction: Would execute {}.{} with params {:?}",
             self.plugin_id, self.function_name, self.parameters
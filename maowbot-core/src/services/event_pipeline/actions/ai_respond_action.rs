@@ -136,52 +136,49 @@ impl EventAction for AiRespondAction {
         
         // Send response if configured
         if self.send_response {
-            match &context.event {
-                BotEvent::ChatMessage { platform, channel, .. } => {
-                    let message = if self.response_prefix.is_empty() {
-                        ai_response.clone()
-                    } else {
-                        format!("{} {}", self.response_prefix, ai_response)
-                    };
-                    
-                    // Get account from platform
-                    let account = match platform.as_str() {
-                        "twitch" => "default", // TODO: Get from context
-                        "discord" => "default",
-                        _ => "default",
-                    };
-                    
-                    // Send via appropriate platform
-                    match platform.as_str() {
-                        "twitch" => {
-                            let user_id = uuid::Uuid::new_v4(); // TODO: Get proper user ID
-                            context.context.message_sender
-                                .send_twitch_message(
-                                    channel,
-                                    &message,
-                                    None,
-                                    user_id,
-                                )
-                                .await?;
-                        }
-                        "discord" => {
-                            // TODO: Get guild ID from context
-                            let guild_id = "";
-                            context.context.platform_manager
-                                .send_discord_message(
-                                    account,
-                                    guild_id,
-                                    channel,
-                                    &message,
-                                )
-                                .await?;
-                        }
-                        _ => {
-                            return Ok(ActionResult::Error(format!("Unsupported platform: {}", platform)));
-                        }
+            if let BotEvent::ChatMessage { platform, channel, .. } = &context.event {
+                let message = if self.response_prefix.is_empty() {
+                    ai_response.clone()
+                } else {
+                    format!("{} {}", self.response_prefix, ai_response)
+                };
+                
+                // Get account from platform
+                let account = match platform.as_str() {
+                    "twitch" => "default", // TODO: Get from context
+                    "discord" => "default",
+                    _ => "default",
+                };
+                
+                // Send via appropriate platform
+                match platform.as_str() {
+                    "twitch" => {
+                        let user_id = uuid::Uuid::new_v4(); // TODO: Get proper user ID
+                        context.context.message_sender
+                            .send_twitch_message(
+                                channel,
+                                &message,
+                                None,
+                                user_id,
+                            )
+                            .await?;
+                    }
+                    "discord" => {
+                        // TODO: Get guild ID from context
+                        let guild_id = "";
+                        context.context.platform_manager
+                            .send_discord_message(
+                                account,
+                                guild_id,
+                                channel,
+                                &message,
+                            )
+                            .await?;
+                    }
+                    _ => {
+                        return Ok(ActionResult::Error(format!("Unsupported platform: {}", platform)));
                     }
                 }
-                _ => {}
             }
         }
         
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::Error;
+use crate::services::event_pipeline::{Condition, EventAction, ActionResult, ActionContext};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BranchActionConfig {
+    #[serde(rename = "if")]
+    condition: String,
+    #[serde(default)]
+    then: Option<Value>,
+    #[serde(default, rename = "else")]
+    otherwise: Option<Value>,
+}
+
+/// Where a branch goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BranchTarget {
+    /// The next action
+    Next,
+    /// End the pipeline
+    Stop,
+    /// The first action with at least this order
+    Order(i32),
+}
+
+impl BranchTarget {
+    fn parse(value: Option<&Value>) -> Result<Self, Error> {
+        match value {
+            None | Some(Value::Null) => Ok(BranchTarget::Next),
+            Some(Value::Number(n)) => n.as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+                .map(BranchTarget::Order)
+                .ok_or_else(|| Error::Platform(format!("Branch target {} is not an action order", n))),
+            Some(Value::String(s)) => match s.trim().to_lowercase().as_str() {
+                "next" | "" => Ok(BranchTarget::Next),
+                "stop" => Ok(BranchTarget::Stop),
+                other => other.parse().map(BranchTarget::Order)
+                    .map_err(|_| Error::Platform(format!("Branch target must be an action order, \"next\" or \"stop\", not '{}'", s))),
+            },
+            Some(other) => Err(Error::Platform(format!("Invalid branch target {}", other))),
+        }
+    }
+}
+
+/// Action that evaluates a condition over the pipeline variables and
+/// continues at `then` or `else`: an action order to jump ahead to,
+/// "stop", or "next" (the default).
+pub struct BranchAction {
+    condition: Option<Condition>,
+    then: BranchTarget,
+    otherwise: BranchTarget,
+}
+
+impl BranchAction {
+    pub fn new() -> Self {
+        Self {
+            condition: None,
+            then: BranchTarget::Next,
+            otherwise: BranchTarget::Next,
+        }
+    }
+}
+
+impl Default for BranchAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventAction for BranchAction {
+    fn id(&self) -> &str {
+        "branch"
+    }
+
+    fn name(&self) -> &str {
+        "Branch"
+    }
+
+    fn configure(&mut self, config: Value) -> Result<(), Error> {
+        let config: BranchActionConfig = serde_json::from_value(config)
+            .map_err(|e| Error::Platform(format!("Invalid branch action config: {}", e)))?;
+
+        self.condition = Some(Condition::parse(&config.condition)?);
+        self.then = BranchTarget::parse(config.then.as_ref())?;
+        self.otherwise = BranchTarget::parse(config.otherwise.as_ref())?;
+        Ok(())
+    }
+
+    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+        let condition = self.condition.as_ref()
+            .ok_or_else(|| Error::Platform("Branch action has no condition".into()))?;
+        let matched = condition.evaluate(&context.shared_data);
+        let target = if matched { self.then } else { self.otherwise };
+        match target {
+            BranchTarget::Next => {}
+            BranchTarget::Stop => context.stop(),
+            BranchTarget::Order(order) => context.go_to(order),
+        }
+        Ok(ActionResult::Success(serde_json::json!({
+            "matched": matched,
+            "target": format!("{:?}", target)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_branch_targets() {
+        assert_eq!(BranchTarget::parse(None).unwrap(), BranchTarget::Next);
+        assert_eq!(BranchTarget::parse(Some(&json!("stop"))).unwrap(), BranchTarget::Stop);
+        assert_eq!(BranchTarget::parse(Some(&json!(30))).unwrap(), BranchTarget::Order(30));
+        assert_eq!(BranchTarget::parse(Some(&json!("30"))).unwrap(), BranchTarget::Order(30));
+        assert!(BranchTarget::parse(Some(&json!("later"))).is_err());
+        assert!(BranchAction::new().configure(json!({"if": "bits >=", "then": 10})).is_err());
+    }
+}
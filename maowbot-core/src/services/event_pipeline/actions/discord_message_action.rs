@@ -46,6 +46,36 @@ impl DiscordMessageAction {
             BotEvent::TwitchEventSub(event) => {
                 message = message.replace("{event_type}", &format!("{:?}", event));
             }
+            BotEvent::Kick(data) => {
+                message = message.replace("{platform}", "kick");
+                message = message.replace("{user}", data.username());
+                message = message.replace("{event_type}", &context.event.event_type());
+            }
+            BotEvent::Donation(donation) => {
+                message = message.replace("{platform}", &donation.source.to_string());
+                message = message.replace("{user}", &donation.donor_name);
+                message = message.replace("{amount}", &donation.formatted_amount());
+                message = message.replace("{message}", donation.message.as_deref().unwrap_or(""));
+            }
+            BotEvent::CredentialRefreshFailed { platform, user_name, expires_at, error, .. } => {
+                let expires = expires_at.map(|t| t.to_rfc3339()).unwrap_or_else(|| "unknown".to_string());
+                message = message.replace("{platform}", platform);
+                message = message.replace("{user}", user_name);
+                message = message.replace("{expires_at}", &expires);
+                message = message.replace("{error}", error);
+            }
+            BotEvent::PlatformConnectionChanged { platform, account_name, state, attempt, error, .. } => {
+                message = message.replace("{platform}", platform);
+                message = message.replace("{user}", account_name);
+                message = message.replace("{state}", state);
+                message = message.replace("{attempt}", &attempt.to_string());
+                message = message.replace("{error}", error.as_deref().unwrap_or(""));
+            }
+            BotEvent::ConfigChanged { key, old_value, new_value, .. } => {
+                message = message.replace("{key}", key);
+                message = message.replace("{old_value}", old_value.as_deref().unwrap_or(""));
+                message = message.replace("{new_value}", new_value.as_deref().unwrap_or(""));
+            }
             _ => {}
         }
         
@@ -0,0 +1,472 @@
+// File: maowbot-core/src/services/event_pipeline/actions/http_request_action.rs
+//
+// Calls an HTTP endpoint (a webhook, a home automation hub, a custom API)
+// when a pipeline runs. The URL, header values and the strings inside the
+// JSON body are templates: `{user}`, `{amount}` and the other event fields
+// from `event_variables`, any string left by an earlier action, and
+// `{secret:<key>}` for tokens stored encrypted with `config set-secret`,
+// which are decrypted at send time and never logged or written to the
+// execution log. A body string that is
+// exactly one placeholder keeps the field's JSON type, so `"{amount}"`
+// sends a number.
+//
+// The response lands in pipeline variables for later actions:
+// `{<capture_as>_status}`, `{<capture_as>_body}`, the parsed JSON under
+// `<capture_as>_json`, and each `capture` entry (a JSON pointer into the
+// response) under its own name.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::{debug, warn};
+use crate::Error;
+use crate::services::event_pipeline::{EventAction, ActionResult, ActionContext};
+use crate::services::event_pipeline::variables::event_variables;
+
+const SECRET_PREFIX: &str = "secret:";
+const MAX_RETRIES: u32 = 5;
+const MAX_TIMEOUT_MS: u64 = 60_000;
+/// Response bodies longer than this are cut before they are stored.
+const MAX_CAPTURED_BODY: usize = 64 * 1024;
+
+fn default_method() -> String {
+    "POST".to_string()
+}
+
+fn default_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_retry_delay_ms() -> u64 {
+    500
+}
+
+fn default_capture_as() -> String {
+    "http".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HttpRequestActionConfig {
+    #[serde(default = "default_method")]
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    body: Option<Value>,
+    #[serde(default = "default_timeout_ms")]
+    timeout_ms: u64,
+    #[serde(default)]
+    retries: u32,
+    #[serde(default = "default_retry_delay_ms")]
+    retry_delay_ms: u64,
+    #[serde(default = "default_capture_as")]
+    capture_as: String,
+    /// variable name => JSON pointer into the response, e.g. "/data/id"
+    #[serde(default)]
+    capture: HashMap<String, String>,
+}
+
+/// Action that sends an HTTP request and keeps the response for later actions
+pub struct HttpRequestAction {
+    client: reqwest::Client,
+    method: Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Value>,
+    timeout: Duration,
+    retries: u32,
+    retry_delay: Duration,
+    capture_as: String,
+    capture: Vec<(String, String)>,
+}
+
+impl HttpRequestAction {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            method: Method::POST,
+            url: String::new(),
+            headers: Vec::new(),
+            body: None,
+            timeout: Duration::from_millis(default_timeout_ms()),
+            retries: 0,
+            retry_delay: Duration::from_millis(default_retry_delay_ms()),
+            capture_as: default_capture_as(),
+            capture: Vec::new(),
+        }
+    }
+
+    /// Every `{secret:...}` key used by the URL, headers or body.
+    fn secret_keys(&self) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        secret_keys_in(&self.url, &mut keys);
+        for (_, value) in &self.headers {
+            secret_keys_in(value, &mut keys);
+        }
+        if let Some(body) = &self.body {
+            secret_keys_in_value(body, &mut keys);
+        }
+        keys
+    }
+
+    /// Values for the placeholders: event fields, then strings set by earlier
+    /// actions, then the secrets (so neither can shadow a secret).
+    async fn placeholders(&self, context: &ActionContext) -> Result<Map<String, Value>, String> {
+        let mut fields = event_variables(&context.event);
+        for (key, value) in &context.shared_data {
+            fields.insert(key.clone(), value.clone());
+        }
+        for key in self.secret_keys() {
+            let value = context.context.bot_config_repo.get_secret(&key).await
+                .map_err(|e| format!("couldn't read secret '{}': {}", key, e))?
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| format!("secret '{}' is not set (use `config set-secret {} <value>`)", key, key))?;
+            fields.insert(format!("{SECRET_PREFIX}{key}"), Value::String(value));
+        }
+        Ok(fields)
+    }
+
+    /// Sends the request, retrying network errors, timeouts, 429s and 5xx
+    /// responses. Returns the last response or the last error.
+    async fn send(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&Value>,
+    ) -> Result<(u16, String), String> {
+        let mut attempt = 0;
+        loop {
+            let mut request = self.client
+                .request(self.method.clone(), url)
+                .timeout(self.timeout);
+            for (name, value) in headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+
+            let outcome = match request.send().await {
+                Ok(resp) => {
+                    let status = resp.status().as_u16();
+                    match resp.text().await {
+                        Ok(text) => Ok((status, text)),
+                        Err(e) => Err(format!("reading the response failed: {}", e)),
+                    }
+                }
+                Err(e) if e.is_timeout() => Err(format!("timed out after {}ms", self.timeout.as_millis())),
+                Err(e) => Err(format!("request failed: {}", e.without_url())),
+            };
+
+            let retry = match &outcome {
+                Ok((status, _)) => is_retryable_status(*status),
+                Err(_) => true,
+            };
+            if !retry || attempt >= self.retries {
+                return outcome;
+            }
+            let delay = self.retry_delay * 2u32.saturating_pow(attempt);
+            debug!(
+                "http_request: attempt {} failed ({}), retrying in {}ms",
+                attempt + 1,
+                match &outcome {
+                    Ok((status, _)) => format!("HTTP {}", status),
+                    Err(e) => e.clone(),
+                },
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Stores the response in the pipeline variables.
+    fn capture_response(&self, context: &mut ActionContext, status: u16, body: &str) {
+        let prefix = &self.capture_as;
+        context.set_data(&format!("{prefix}_status"), Value::String(status.to_string()));
+        context.set_data(&format!("{prefix}_body"), Value::String(truncate(body, MAX_CAPTURED_BODY)));
+
+        let Ok(json) = serde_json::from_str::<Value>(body) else {
+            return;
+        };
+        for (name, pointer) in &self.capture {
+            match json.pointer(pointer) {
+                Some(value) => context.set_data(name, as_variable(value)),
+                None => debug!("http_request: response has nothing at '{}' for '{}'", pointer, name),
+            }
+        }
+        context.set_data(&format!("{prefix}_json"), json);
+    }
+}
+
+impl Default for HttpRequestAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventAction for HttpRequestAction {
+    fn id(&self) -> &str {
+        "http_request"
+    }
+
+    fn name(&self) -> &str {
+        "HTTP Request"
+    }
+
+    fn configure(&mut self, config: Value) -> Result<(), Error> {
+        let config: HttpRequestActionConfig = serde_json::from_value(config)
+            .map_err(|e| Error::Platform(format!("Invalid HTTP request action config: {}", e)))?;
+
+        self.method = Method::from_bytes(config.method.trim().to_uppercase().as_bytes())
+            .map_err(|_| Error::Platform(format!("Invalid HTTP method '{}'", config.method)))?;
+        let url = config.url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://") || url.starts_with('{')) {
+            return Err(Error::Platform(format!("http_request URL must start with http:// or https://: '{}'", url)));
+        }
+        if config.retries > MAX_RETRIES {
+            return Err(Error::Platform(format!("http_request allows at most {} retries", MAX_RETRIES)));
+        }
+        if config.capture_as.trim().is_empty() {
+            return Err(Error::Platform("http_request capture_as can't be empty".into()));
+        }
+
+        self.url = url.to_string();
+        self.headers = config.headers.into_iter().collect();
+        self.headers.sort();
+        self.body = config.body;
+        self.timeout = Duration::from_millis(config.timeout_ms.clamp(1, MAX_TIMEOUT_MS));
+        self.retries = config.retries;
+        self.retry_delay = Duration::from_millis(config.retry_delay_ms);
+        self.capture_as = config.capture_as.trim().to_string();
+        self.capture = config.capture.into_iter().collect();
+        self.capture.sort();
+        Ok(())
+    }
+
+    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+        let fields = match self.placeholders(context).await {
+            Ok(fields) => fields,
+            Err(e) => return Ok(ActionResult::Error(e)),
+        };
+
+        let url = fill(&self.url, &fields);
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Ok(ActionResult::Error(format!("'{}' is not an http(s) URL", self.url)));
+        }
+        let headers: Vec<(String, String)> = self.headers.iter()
+            .map(|(name, value)| (name.clone(), fill(value, &fields)))
+            .collect();
+        let body = self.body.as_ref().map(|b| render_body(b, &fields));
+        // What the execution log shows: the URL with its secrets left as placeholders
+        let shown_url = fill(&self.url, &without_secrets(&fields));
+
+        let started = std::time::Instant::now();
+        let (status, text) = match self.send(&url, &headers, body.as_ref()).await {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("http_request: {} {} failed: {}", self.method, shown_url, e);
+                return Ok(ActionResult::Error(format!("{} {}: {}", self.method, shown_url, e)));
+            }
+        };
+        let latency_ms = started.elapsed().as_millis() as u64;
+        self.capture_response(context, status, &text);
+
+        if status >= 400 {
+            return Ok(ActionResult::Error(format!(
+                "{} {} returned HTTP {}: {}",
+                self.method, shown_url, status, truncate(text.trim(), 200)
+            )));
+        }
+        Ok(ActionResult::Success(serde_json::json!({
+            "method": self.method.as_str(),
+            "url": shown_url,
+            "status": status,
+            "latency_ms": latency_ms,
+            "response_length": text.len()
+        })))
+    }
+}
+
+/// Replaces each `{name}` in `template` whose name is in `fields`; any other
+/// braces are left alone.
+fn fill(template: &str, fields: &Map<String, Value>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}').and_then(|close| lookup(fields, &after[..close]).map(|v| (close, v))) {
+            Some((close, value)) => {
+                out.push_str(&as_text(value));
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The value for a placeholder name. Secret keys are trimmed as in
+/// `secret_keys_in`, so `{secret: hook.token}` finds `secret:hook.token`.
+fn lookup<'a>(fields: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    match name.strip_prefix(SECRET_PREFIX) {
+        Some(key) => fields.get(&format!("{SECRET_PREFIX}{}", key.trim())),
+        None => fields.get(name),
+    }
+}
+
+/// Fills the strings in a JSON body. A string that is exactly one known
+/// placeholder becomes that field's value, keeping numbers and booleans typed.
+fn render_body(body: &Value, fields: &Map<String, Value>) -> Value {
+    match body {
+        Value::String(s) => {
+            let whole = s.strip_prefix('{')
+                .and_then(|s| s.strip_suffix('}'))
+                .filter(|name| !name.contains(['{', '}']))
+                .and_then(|name| lookup(fields, name));
+            match whole {
+                Some(value) => value.clone(),
+                None => Value::String(fill(s, fields)),
+            }
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| render_body(v, fields)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter().map(|(k, v)| (k.clone(), render_body(v, fields))).collect()
+        ),
+        other => other.clone(),
+    }
+}
+
+fn secret_keys_in(template: &str, keys: &mut BTreeSet<String>) {
+    let mut rest = template;
+    while let Some(start) = rest.find(&format!("{{{SECRET_PREFIX}")) {
+        let after = &rest[start + 1 + SECRET_PREFIX.len()..];
+        let Some(close) = after.find('}') else { break };
+        let key = after[..close].trim();
+        if !key.is_empty() {
+            keys.insert(key.to_string());
+        }
+        rest = &after[close + 1..];
+    }
+}
+
+fn secret_keys_in_value(value: &Value, keys: &mut BTreeSet<String>) {
+    match value {
+        Value::String(s) => secret_keys_in(s, keys),
+        Value::Array(items) => items.iter().for_each(|v| secret_keys_in_value(v, keys)),
+        Value::Object(map) => map.values().for_each(|v| secret_keys_in_value(v, keys)),
+        _ => {}
+    }
+}
+
+fn without_secrets(fields: &Map<String, Value>) -> Map<String, Value> {
+    fields.iter()
+        .filter(|(k, _)| !k.starts_with(SECRET_PREFIX))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Scalars are stored as strings so every action's `{name}` placeholders can
+/// use them; objects and arrays stay JSON.
+fn as_variable(value: &Value) -> Value {
+    match value {
+        Value::Object(_) | Value::Array(_) => value.clone(),
+        other => Value::String(as_text(other)),
+    }
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[..end].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields() -> Map<String, Value> {
+        let mut fields = Map::new();
+        fields.insert("user".into(), json!("Mochi"));
+        fields.insert("amount".into(), json!(5.5));
+        fields.insert("secret:webhook.token".into(), json!("s3cret"));
+        fields
+    }
+
+    #[test]
+    fn test_fills_known_placeholders_only() {
+        let fields = fields();
+        assert_eq!(fill("{user} tipped {amount} {unknown} {", &fields), "Mochi tipped 5.5 {unknown} {");
+        assert_eq!(fill("Bearer {secret:webhook.token}", &fields), "Bearer s3cret");
+        assert_eq!(fill("{{user}}", &fields), "{Mochi}");
+    }
+
+    #[test]
+    fn test_secret_placeholders_allow_spaces_around_the_key() {
+        let mut keys = BTreeSet::new();
+        secret_keys_in("Bearer {secret: webhook.token }", &mut keys);
+        assert_eq!(keys.into_iter().collect::<Vec<_>>(), vec!["webhook.token"]);
+        assert_eq!(fill("Bearer {secret: webhook.token }", &fields()), "Bearer s3cret");
+        assert_eq!(render_body(&json!("{secret: webhook.token}"), &fields()), json!("s3cret"));
+    }
+
+    #[test]
+    fn test_renders_typed_json_body() {
+        let body = json!({"who": "{user}", "amount": "{amount}", "text": "{user} gave {amount}", "tags": ["{user}", 1]});
+        assert_eq!(
+            render_body(&body, &fields()),
+            json!({"who": "Mochi", "amount": 5.5, "text": "Mochi gave 5.5", "tags": ["Mochi", 1]})
+        );
+    }
+
+    #[test]
+    fn test_finds_secret_keys() {
+        let mut action = HttpRequestAction::new();
+        action.configure(json!({
+            "url": "https://example.com/hook?key={secret:hook.key}",
+            "headers": {"Authorization": "Bearer {secret:hook.token}"},
+            "body": {"nested": ["{secret:hook.token}", "{user}"]},
+            "retries": 2
+        })).unwrap();
+        let keys: Vec<_> = action.secret_keys().into_iter().collect();
+        assert_eq!(keys, vec!["hook.key", "hook.token"]);
+        assert_eq!(fill(&action.url, &without_secrets(&fields())), "https://example.com/hook?key={secret:hook.key}");
+    }
+
+    #[test]
+    fn test_rejects_bad_config() {
+        let mut action = HttpRequestAction::new();
+        assert!(action.configure(json!({"url": "ftp://example.com"})).is_err());
+        assert!(action.configure(json!({"url": "https://example.com", "method": "NOT A METHOD"})).is_err());
+        assert!(action.configure(json!({"url": "https://example.com", "retries": 10})).is_err());
+        assert!(is_retryable_status(503) && is_retryable_status(429) && !is_retryable_status(404));
+    }
+}
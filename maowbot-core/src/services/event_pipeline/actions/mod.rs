@@ -9,6 +9,10 @@ mod obs_scene_change_action;
 mod obs_source_toggle_action;
 mod plugin_call_action;
 mod ai_respond_action;
+mod post_status_action;
+mod http_request_action;
+mod branch_action;
+mod stop_action;
 
 pub use log_action::LogAction;
 pub use discord_message_action::DiscordMessageAction;
@@ -20,4 +24,8 @@ pub use osc_trigger_action::OscTriggerAction;
 pub use obs_scene_change_action::ObsSceneChangeAction;
 pub use obs_source_toggle_action::ObsSourceToggleAction;
 pub use plugin_call_action::PluginCallAction;
-pub use ai_respond_action::AiRespondAction;
\ No newline at end of file
+pub use ai_respond_action::AiRespondAction;
+pub use post_status_action::PostStatusAction;
+pub use http_request_action::HttpRequestAction;
+pub use branch_action::BranchAction;
+pub use stop_action::StopAction;
\ No newline at end of file
@@ -60,7 +60,7 @@ impl EventAction for ObsSceneChangeAction {
         Ok(())
     }
 
-    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+    async fn execute(&self, _context: &mut ActionContext) -> Result<ActionResult, Error> {
         // Get OBS instance name (default to first/primary instance if not specified)
         let instance_name = if !self.instance_name.is_empty() {
             &self.instance_name
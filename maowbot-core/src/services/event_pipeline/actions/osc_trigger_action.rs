@@ -64,7 +64,7 @@ impl EventAction for OscTriggerAction {
         Ok(())
     }
 
-    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+    async fn execute(&self, _context: &mut ActionContext) -> Result<ActionResult, Error> {
         // Use toggle ID if specified, otherwise use direct parameter
         if let Some(toggle_id) = &self.toggle_id {
             // TODO: Implement toggle by ID functionality
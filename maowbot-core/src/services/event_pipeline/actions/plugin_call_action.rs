@@ -43,15 +43,12 @@ impl PluginCallAction {
                     let mut s = s.clone();
                     
                     // Replace event placeholders
-                    match &context.event {
-                        crate::eventbus::BotEvent::ChatMessage { platform, channel, user, text, .. } => {
-                            s = s.replace("{platform}", platform);
-                            s = s.replace("{channel}", channel);
-                            s = s.replace("{user}", user);
-                            s = s.replace("{message}", text);
-                            s = s.replace("{text}", text);
-                        }
-                        _ => {}
+                    if let crate::eventbus::BotEvent::ChatMessage { platform, channel, user, text, .. } = &context.event {
+                        s = s.replace("{platform}", platform);
+                        s = s.replace("{channel}", channel);
+                        s = s.replace("{user}", user);
+                        s = s.replace("{message}", text);
+                        s = s.replace("{text}", text);
                     }
                     
                     // Replace shared data placeholders
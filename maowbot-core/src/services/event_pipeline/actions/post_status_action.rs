@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use crate::Error;
+use crate::eventbus::{BotEvent, TwitchEventSubData};
+use crate::platforms::twitch::requests::stream::fetch_stream_details;
+use crate::services::event_pipeline::{EventAction, ActionResult, ActionContext};
+use crate::services::social::{self, SocialNetwork};
+
+fn default_networks() -> Vec<String> {
+    vec!["bluesky".to_string(), "mastodon".to_string()]
+}
+
+fn default_template() -> String {
+    "{user} is live: {title} {link}".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PostStatusActionConfig {
+    #[serde(default = "default_networks")]
+    networks: Vec<String>,
+    #[serde(default = "default_template")]
+    message_template: String,
+}
+
+/// Action that posts a status to Bluesky and/or Mastodon
+pub struct PostStatusAction {
+    networks: Vec<SocialNetwork>,
+    message_template: String,
+}
+
+impl PostStatusAction {
+    pub fn new() -> Self {
+        Self {
+            networks: vec![SocialNetwork::Bluesky, SocialNetwork::Mastodon],
+            message_template: default_template(),
+        }
+    }
+
+    /// Values for {user}, {title}, {category} and {link}. stream.online carries
+    /// no title, so it is looked up on Helix (left blank if that fails).
+    async fn placeholders(&self, context: &ActionContext) -> Vec<(&'static str, String)> {
+        let mut values = Vec::new();
+        match &context.event {
+            BotEvent::TwitchEventSub(TwitchEventSubData::StreamOnline(evt)) => {
+                values.push(("user", evt.broadcaster_user_name.clone()));
+                values.push(("link", format!("https://twitch.tv/{}", evt.broadcaster_user_login)));
+                values.push(("platform", "twitch".to_string()));
+                let details = match context.context.platform_manager.get_twitch_client().await {
+                    Some(client) => fetch_stream_details(&client, &evt.broadcaster_user_id).await.ok(),
+                    None => None,
+                };
+                match details {
+                    Some(d) => {
+                        values.push(("title", d.stream_title));
+                        values.push(("category", d.game));
+                    }
+                    None => warn!("post_status: could not fetch stream details for '{}'", evt.broadcaster_user_login),
+                }
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelUpdate(evt)) => {
+                values.push(("user", evt.broadcaster_user_name.clone()));
+                values.push(("link", format!("https://twitch.tv/{}", evt.broadcaster_user_login)));
+                values.push(("platform", "twitch".to_string()));
+                values.push(("title", evt.title.clone()));
+                values.push(("category", evt.category_name.clone()));
+            }
+            BotEvent::Kick(data) => {
+                values.push(("user", data.username().to_string()));
+                values.push(("platform", "kick".to_string()));
+            }
+            BotEvent::ChatMessage { platform, channel, user, text, .. } => {
+                values.push(("user", user.clone()));
+                values.push(("platform", platform.clone()));
+                values.push(("channel", channel.clone()));
+                values.push(("message", text.clone()));
+            }
+            _ => {}
+        }
+        values
+    }
+
+    async fn format_message(&self, context: &ActionContext) -> String {
+        let mut message = self.message_template.clone();
+        for (key, value) in self.placeholders(context).await {
+            message = message.replace(&format!("{{{}}}", key), &value);
+        }
+
+        // Replace shared data placeholders
+        for (key, value) in &context.shared_data {
+            if let Some(str_val) = value.as_str() {
+                message = message.replace(&format!("{{{}}}", key), str_val);
+            }
+        }
+
+        // Placeholders with no value for this event are dropped
+        for key in ["user", "title", "category", "link", "platform", "channel", "message"] {
+            message = message.replace(&format!("{{{}}}", key), "");
+        }
+        message.trim().to_string()
+    }
+}
+
+impl Default for PostStatusAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventAction for PostStatusAction {
+    fn id(&self) -> &str {
+        "post_status"
+    }
+
+    fn name(&self) -> &str {
+        "Post Status"
+    }
+
+    fn configure(&mut self, config: serde_json::Value) -> Result<(), Error> {
+        let config: PostStatusActionConfig = serde_json::from_value(config)
+            .map_err(|e| Error::Platform(format!("Invalid post status action config: {}", e)))?;
+
+        self.networks = config.networks.iter()
+            .map(|n| n.parse())
+            .collect::<Result<Vec<SocialNetwork>, Error>>()?;
+        if self.networks.is_empty() {
+            return Err(Error::Platform("post_status needs at least one network".into()));
+        }
+        self.message_template = config.message_template;
+        Ok(())
+    }
+
+    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+        let message = self.format_message(context).await;
+        if message.is_empty() {
+            return Ok(ActionResult::Error("Status text is empty".to_string()));
+        }
+
+        let repo = context.context.bot_config_repo.clone();
+        let mut posted = serde_json::Map::new();
+        let mut errors = Vec::new();
+        for network in &self.networks {
+            match social::post_status(repo.as_ref(), *network, &message).await {
+                Ok(status) => {
+                    info!("post_status: posted to {} ({:?})", network, status.url);
+                    posted.insert(network.to_string(), status.url.into());
+                }
+                Err(e) => {
+                    warn!("post_status: {} failed: {:?}", network, e);
+                    errors.push(format!("{}: {}", network, e));
+                }
+            }
+        }
+
+        if posted.is_empty() {
+            return Ok(ActionResult::Error(errors.join("; ")));
+        }
+        context.set_data("status_text", message.clone().into());
+        Ok(ActionResult::Success(serde_json::json!({
+            "posted": posted,
+            "errors": errors,
+            "message_length": message.len()
+        })))
+    }
+}
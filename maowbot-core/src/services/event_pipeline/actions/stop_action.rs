@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::Error;
+use crate::services::event_pipeline::{EventAction, ActionResult, ActionContext};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StopActionConfig {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Action that ends the pipeline without running the actions after it. Give
+/// it a condition to stop only for some events.
+pub struct StopAction {
+    reason: Option<String>,
+}
+
+impl StopAction {
+    pub fn new() -> Self {
+        Self { reason: None }
+    }
+}
+
+impl Default for StopAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventAction for StopAction {
+    fn id(&self) -> &str {
+        "stop"
+    }
+
+    fn name(&self) -> &str {
+        "Stop Pipeline"
+    }
+
+    fn configure(&mut self, config: serde_json::Value) -> Result<(), Error> {
+        let config: StopActionConfig = if config.is_null() {
+            StopActionConfig::default()
+        } else {
+            serde_json::from_value(config)
+                .map_err(|e| Error::Platform(format!("Invalid stop action config: {}", e)))?
+        };
+        self.reason = config.reason.filter(|r| !r.trim().is_empty());
+        Ok(())
+    }
+
+    async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
+        context.stop();
+        Ok(ActionResult::Success(serde_json::json!({
+            "stopped": true,
+            "reason": self.reason
+        })))
+    }
+
+    fn is_parallelizable(&self) -> bool {
+        true
+    }
+}
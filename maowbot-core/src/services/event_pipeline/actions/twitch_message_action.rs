@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use crate::Error;
-use crate::eventbus::BotEvent;
+use crate::eventbus::{BotEvent, TwitchEventSubData};
 use crate::services::event_pipeline::{EventAction, ActionResult, ActionContext};
+use crate::services::twitch::send_whisper;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TwitchMessageActionConfig {
@@ -12,6 +13,22 @@ struct TwitchMessageActionConfig {
     message_template: String,
     #[serde(default)]
     reply_to_message: bool,
+    /// Whisper the user who triggered the event instead of posting in chat
+    #[serde(default)]
+    reply_privately: bool,
+}
+
+/// The Twitch user id of whoever triggered the event, for private replies.
+fn whisper_recipient(event: &BotEvent) -> Option<String> {
+    match event {
+        BotEvent::ChatMessage { platform, metadata, .. } if platform == "twitch-irc" => {
+            metadata.get("platform_user_id").and_then(|v| v.as_str()).map(str::to_string)
+        }
+        BotEvent::TwitchEventSub(TwitchEventSubData::UserWhisperMessage(ev)) => {
+            Some(ev.from_user_id.clone())
+        }
+        _ => None,
+    }
 }
 
 /// Action that sends a Twitch chat message
@@ -20,6 +37,7 @@ pub struct TwitchMessageAction {
     channel: String,
     message_template: String,
     reply_to_message: bool,
+    reply_privately: bool,
 }
 
 impl TwitchMessageAction {
@@ -29,6 +47,7 @@ impl TwitchMessageAction {
             channel: String::new(),
             message_template: String::new(),
             reply_to_message: false,
+            reply_privately: false,
         }
     }
     
@@ -44,6 +63,11 @@ impl TwitchMessageAction {
                 message = message.replace("{message}", text);
                 message = message.replace("{text}", text);
             }
+            BotEvent::TwitchEventSub(TwitchEventSubData::UserWhisperMessage(ev)) => {
+                message = message.replace("{user}", &ev.from_user_name);
+                message = message.replace("{message}", &ev.whisper.text);
+                message = message.replace("{text}", &ev.whisper.text);
+            }
             BotEvent::TwitchEventSub(event) => {
                 message = message.replace("{event_type}", &format!("{:?}", event));
             }
@@ -85,11 +109,24 @@ impl EventAction for TwitchMessageAction {
         self.channel = config.channel;
         self.message_template = config.message_template;
         self.reply_to_message = config.reply_to_message;
+        self.reply_privately = config.reply_privately;
         Ok(())
     }
 
     async fn execute(&self, context: &mut ActionContext) -> Result<ActionResult, Error> {
         let message = self.format_message(context);
+
+        if self.reply_privately {
+            let Some(recipient) = whisper_recipient(&context.event) else {
+                return Ok(ActionResult::Error("No Twitch user to whisper for this event".to_string()));
+            };
+            send_whisper(&context.context.message_sender.platform_manager, &recipient, &message).await?;
+            return Ok(ActionResult::Success(serde_json::json!({
+                "message_sent": true,
+                "whispered_to": recipient,
+                "message_length": message.len()
+            })));
+        }
         
         // Get channel from config or event
         let channel = if !self.channel.is_empty() {
@@ -134,4 +171,57 @@ impl EventAction for TwitchMessageAction {
             "message_length": message.len()
         })))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use crate::platforms::twitch_eventsub::events::{UserWhisperMessage, WhisperText};
+
+    fn chat(platform: &str, platform_user_id: Option<&str>) -> BotEvent {
+        let mut metadata = serde_json::Map::new();
+        if let Some(id) = platform_user_id {
+            metadata.insert("platform_user_id".to_string(), json!(id));
+        }
+        BotEvent::ChatMessage {
+            platform: platform.to_string(),
+            channel: "#cat".to_string(),
+            user: "someone".to_string(),
+            text: "!secret".to_string(),
+            timestamp: Utc::now(),
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_private_replies_go_to_whoever_triggered_the_event() {
+        assert_eq!(whisper_recipient(&chat("twitch-irc", Some("1234"))).as_deref(), Some("1234"));
+        assert_eq!(whisper_recipient(&chat("twitch-irc", None)), None);
+        // Only Twitch users can be whispered
+        assert_eq!(whisper_recipient(&chat("discord", Some("1234"))), None);
+
+        let whisper = BotEvent::TwitchEventSub(TwitchEventSubData::UserWhisperMessage(UserWhisperMessage {
+            from_user_id: "5678".to_string(),
+            from_user_login: "viewer".to_string(),
+            from_user_name: "Viewer".to_string(),
+            to_user_id: "1".to_string(),
+            to_user_login: "bot".to_string(),
+            to_user_name: "Bot".to_string(),
+            whisper_id: "w1".to_string(),
+            whisper: WhisperText { text: "hi".to_string() },
+        }));
+        assert_eq!(whisper_recipient(&whisper).as_deref(), Some("5678"));
+        assert_eq!(whisper_recipient(&BotEvent::Tick), None);
+    }
+
+    #[test]
+    fn test_reply_privately_is_read_from_the_config() {
+        let mut action = TwitchMessageAction::new();
+        action.configure(json!({"account": "bot", "message_template": "psst"})).unwrap();
+        assert!(!action.reply_privately);
+        action.configure(json!({"account": "bot", "message_template": "psst", "reply_privately": true})).unwrap();
+        assert!(action.reply_privately);
+    }
+}
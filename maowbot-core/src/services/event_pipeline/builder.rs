@@ -134,19 +134,19 @@ impl CompositeFilterBuilder {
         }
     }
 
-    pub fn add(self, filter: Box<dyn EventFilter>) -> Self {
+    pub fn add_filter(self, filter: Box<dyn EventFilter>) -> Self {
         Self {
             filter: self.filter.add_filter(filter),
         }
     }
 
     pub fn platform(self, platforms: Vec<Platform>) -> Self {
-        self.add(Box::new(PlatformFilter::new(platforms)))
+        self.add_filter(Box::new(PlatformFilter::new(platforms)))
     }
 
     pub fn channel(self, channels: Vec<&str>) -> Self {
         let channels: Vec<String> = channels.into_iter().map(|s| s.to_string()).collect();
-        self.add(Box::new(ChannelFilter::new(channels)))
+        self.add_filter(Box::new(ChannelFilter::new(channels)))
     }
 
     fn build(self) -> CompositeFilter {
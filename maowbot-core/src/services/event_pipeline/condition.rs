@@ -0,0 +1,368 @@
+// File: maowbot-core/src/services/event_pipeline/condition.rs
+//
+// Small expressions over pipeline variables, used as action conditions,
+// by the `branch` action and the `expression_filter`:
+//
+//     bits >= 1000
+//     if tier == "3000" && !is_gift
+//     (amount > 5 or bpm > 140) and message contains 'hype'
+//
+// Operands are variable names, numbers, quoted strings and true/false.
+// Comparisons are numeric when both sides read as numbers, otherwise string
+// comparisons that ignore case. A missing variable is null: only `!=` holds
+// for it. A bare variable is true unless it is missing, false, 0 or empty.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use serde_json::Value;
+use crate::Error;
+
+/// Deepest nesting of `(` and `!` a condition may use.
+const MAX_NESTING: usize = 32;
+/// Most tokens in one condition; `and`/`or` chains nest as deep as they are long.
+const MAX_TOKENS: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Var(String),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    Truthy(Operand),
+    Compare(Operand, CompareOp, Operand),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Str(s) => write!(f, "\"{}\"", s),
+            Token::Op(op) => write!(f, "{:?}", op),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Not => write!(f, "!"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn invalid(expr: &str, why: impl fmt::Display) -> Error {
+    Error::Parse(format!("Invalid condition '{}': {}", expr, why))
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, Error> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => { tokens.push(Token::Open); i += 1; }
+            ')' => { tokens.push(Token::Close); i += 1; }
+            '&' if next == Some('&') => { tokens.push(Token::And); i += 2; }
+            '|' if next == Some('|') => { tokens.push(Token::Or); i += 2; }
+            '=' if next == Some('=') => { tokens.push(Token::Op(CompareOp::Eq)); i += 2; }
+            '!' if next == Some('=') => { tokens.push(Token::Op(CompareOp::Ne)); i += 2; }
+            '>' if next == Some('=') => { tokens.push(Token::Op(CompareOp::Ge)); i += 2; }
+            '<' if next == Some('=') => { tokens.push(Token::Op(CompareOp::Le)); i += 2; }
+            '>' => { tokens.push(Token::Op(CompareOp::Gt)); i += 1; }
+            '<' => { tokens.push(Token::Op(CompareOp::Lt)); i += 1; }
+            '!' => { tokens.push(Token::Not); i += 1; }
+            '"' | '\'' => {
+                let end = chars[i + 1..].iter().position(|&ch| ch == c)
+                    .ok_or_else(|| invalid(expr, "unclosed quote"))?;
+                tokens.push(Token::Str(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let len = chars[i + 1..].iter()
+                    .position(|ch| !(ch.is_ascii_digit() || *ch == '.'))
+                    .unwrap_or(chars.len() - i - 1) + 1;
+                let text: String = chars[i..i + len].iter().collect();
+                let number = text.parse().map_err(|_| invalid(expr, format!("bad number {}", text)))?;
+                tokens.push(Token::Number(number));
+                i += len;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let len = chars[i..].iter()
+                    .position(|ch| !(ch.is_alphanumeric() || *ch == '_' || *ch == '.'))
+                    .unwrap_or(chars.len() - i);
+                let word: String = chars[i..i + len].iter().collect();
+                tokens.push(match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "contains" => Token::Op(CompareOp::Contains),
+                    _ => Token::Ident(word),
+                });
+                i += len;
+            }
+            other => return Err(invalid(expr, format!("unexpected '{}'", other))),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Condition, Error> {
+        let mut left = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            left = Condition::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Condition, Error> {
+        let mut left = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            left = Condition::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Condition, Error> {
+        if !matches!(self.peek(), Some(Token::Not | Token::Open)) {
+            return self.comparison();
+        }
+        if self.depth >= MAX_NESTING {
+            return Err(invalid(self.expr, format!("it nests deeper than {} levels", MAX_NESTING)));
+        }
+        self.depth += 1;
+        let condition = match self.next() {
+            Some(Token::Not) => self.unary().map(|inner| Condition::Not(Box::new(inner))),
+            _ => self.or().and_then(|inner| match self.next() {
+                Some(Token::Close) => Ok(inner),
+                _ => Err(invalid(self.expr, "missing ')'")),
+            }),
+        };
+        self.depth -= 1;
+        condition
+    }
+
+    fn comparison(&mut self) -> Result<Condition, Error> {
+        let left = self.operand()?;
+        match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.pos += 1;
+                Ok(Condition::Compare(left, op, self.operand()?))
+            }
+            _ => Ok(Condition::Truthy(left)),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Operand::Literal(serde_json::json!(n))),
+            Some(Token::Str(s)) => Ok(Operand::Literal(Value::String(s))),
+            Some(Token::Ident(word)) => Ok(match word.to_lowercase().as_str() {
+                "true" => Operand::Literal(Value::Bool(true)),
+                "false" => Operand::Literal(Value::Bool(false)),
+                "null" => Operand::Literal(Value::Null),
+                _ => Operand::Var(word),
+            }),
+            Some(other) => Err(invalid(self.expr, format!("expected a value, found {}", other))),
+            None => Err(invalid(self.expr, "ends too early")),
+        }
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !(s.is_empty() || s == "0" || s.eq_ignore_ascii_case("false")),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn compare(left: &Value, op: CompareOp, right: &Value) -> bool {
+    if op == CompareOp::Contains {
+        return !left.is_null() && text(left).to_lowercase().contains(&text(right).to_lowercase());
+    }
+    if left.is_null() || right.is_null() {
+        let same = left.is_null() && right.is_null();
+        return match op {
+            CompareOp::Eq => same,
+            CompareOp::Ne => !same,
+            _ => false,
+        };
+    }
+    let ordering = match (number(left), number(right), left, right) {
+        (_, _, Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Some(a), Some(b), _, _) => match a.partial_cmp(&b) {
+            Some(ordering) => ordering,
+            None => return false,
+        },
+        _ => text(left).to_lowercase().cmp(&text(right).to_lowercase()),
+    };
+    match op {
+        CompareOp::Eq => ordering == Ordering::Equal,
+        CompareOp::Ne => ordering != Ordering::Equal,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Ge => ordering != Ordering::Less,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Le => ordering != Ordering::Greater,
+        CompareOp::Contains => unreachable!(),
+    }
+}
+
+impl Condition {
+    /// Parses an expression; a leading `if` is allowed.
+    pub fn parse(expr: &str) -> Result<Self, Error> {
+        let trimmed = expr.trim();
+        let body = match trimmed.split_once(char::is_whitespace) {
+            Some((first, rest)) if first.eq_ignore_ascii_case("if") => rest,
+            _ => trimmed,
+        };
+        let mut parser = Parser { expr, tokens: tokenize(body)?, pos: 0, depth: 0 };
+        if parser.tokens.is_empty() {
+            return Err(invalid(expr, "it is empty"));
+        }
+        if parser.tokens.len() > MAX_TOKENS {
+            return Err(invalid(expr, format!("it has more than {} parts", MAX_TOKENS)));
+        }
+        let condition = parser.or()?;
+        if let Some(extra) = parser.peek() {
+            return Err(invalid(expr, format!("unexpected {}", extra)));
+        }
+        Ok(condition)
+    }
+
+    pub fn evaluate(&self, vars: &HashMap<String, Value>) -> bool {
+        let value = |operand: &Operand| match operand {
+            Operand::Var(name) => vars.get(name).cloned().unwrap_or(Value::Null),
+            Operand::Literal(v) => v.clone(),
+        };
+        match self {
+            Condition::Truthy(operand) => truthy(&value(operand)),
+            Condition::Compare(left, op, right) => compare(&value(left), *op, &value(right)),
+            Condition::Not(inner) => !inner.evaluate(vars),
+            Condition::And(a, b) => a.evaluate(vars) && b.evaluate(vars),
+            Condition::Or(a, b) => a.evaluate(vars) || b.evaluate(vars),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn vars() -> HashMap<String, Value> {
+        HashMap::from([
+            ("bits".to_string(), json!(1500)),
+            ("tier".to_string(), json!("3000")),
+            ("is_gift".to_string(), json!(false)),
+            ("user".to_string(), json!("Mochi")),
+            ("message".to_string(), json!("HYPE train!")),
+        ])
+    }
+
+    fn check(expr: &str) -> bool {
+        Condition::parse(expr).unwrap().evaluate(&vars())
+    }
+
+    #[test]
+    fn test_evaluates_comparisons() {
+        assert!(check("if bits >= 1000"));
+        assert!(!check("bits < 1000"));
+        assert!(check("tier == 3000 && !is_gift"));
+        assert!(check("user == 'mochi'"));
+        assert!(check("message contains \"hype\""));
+        assert!(check("bits > 5000 or (user != \"tofu\" and bits >= 100)"));
+        assert!(!check("missing > 1"));
+        assert!(check("missing != 1"));
+        assert!(!check("missing"));
+        assert!(check("not missing"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_expressions() {
+        for expr in ["", "bits >=", "(bits > 1", "bits > 1 1", "bits = 1", "'open"] {
+            assert!(Condition::parse(expr).is_err(), "{expr} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_rejects_deep_nesting_instead_of_overflowing() {
+        let nested = format!("{}bits > 1{}", "(".repeat(MAX_NESTING), ")".repeat(MAX_NESTING));
+        assert!(check(&nested));
+
+        let too_deep = format!("{}bits > 1{}", "(".repeat(MAX_NESTING + 1), ")".repeat(MAX_NESTING + 1));
+        assert!(matches!(Condition::parse(&too_deep), Err(Error::Parse(_))));
+        assert!(matches!(Condition::parse(&format!("{}bits", "!".repeat(100))), Err(Error::Parse(_))));
+        assert!(matches!(Condition::parse(&vec!["bits"; 1000].join(" and ")), Err(Error::Parse(_))));
+    }
+}
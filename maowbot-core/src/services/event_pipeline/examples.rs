@@ -1,7 +1,7 @@
 use crate::services::event_pipeline::{PipelineBuilder, EventPipeline};
 use maowbot_common::models::platform::Platform;
 
-/// Example pipeline configurations demonstrating various use cases
+// Example pipeline configurations demonstrating various use cases
 
 /// Stream announcement pipeline - Twitch stream goes live, notify Discord
 pub fn create_stream_announcement_pipeline() -> EventPipeline {
@@ -23,7 +23,7 @@ pub trait EventFilter: Send + Sync {
     fn name(&self) -> &str;
     
     /// Configure the filter from JSON configuration
-    fn configure(&mut self, config: serde_json::Value) -> Result<(), Error> {
+    fn configure(&mut self, _config: serde_json::Value) -> Result<(), Error> {
         // Default implementation does nothing
         Ok(())
     }
@@ -59,6 +59,7 @@ impl EventFilter for PlatformFilter {
         let platform = match event {
             BotEvent::ChatMessage { platform, .. } => Platform::from_string(platform),
             BotEvent::TwitchEventSub(_) => Platform::TwitchEventSub,
+            BotEvent::Kick(_) => Platform::Kick,
             _ => return Ok(FilterResult::Reject),
         };
 
@@ -106,6 +107,8 @@ impl EventFilter for ChannelFilter {
 }
 
 /// Filter by user roles
+// Passes everything until user roles can be looked up
+#[allow(dead_code)]
 pub struct UserRoleFilter {
     required_roles: Vec<String>,
     match_any: bool, // true = OR, false = AND
@@ -161,7 +164,7 @@ impl MessagePatternFilter {
     pub fn new(patterns: Vec<&str>, match_any: bool) -> Result<Self, Error> {
         let compiled_patterns = patterns
             .into_iter()
-            .map(|p| regex::Regex::new(p))
+            .map(regex::Regex::new)
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| Error::Platform(format!("Invalid regex pattern: {}", e)))?;
         
@@ -306,10 +309,8 @@ impl EventFilter for CompositeFilter {
             }
         }
         
-        // For AND mode, we need all to pass
-        if self.require_all && pass_count == self.filters.len() {
-            Ok(FilterResult::Pass)
-        } else if !self.require_all && pass_count > 0 {
+        // For AND mode, we need all to pass; for OR mode, any one
+        if (self.require_all && pass_count == self.filters.len()) || (!self.require_all && pass_count > 0) {
             Ok(FilterResult::Pass)
         } else {
             Ok(FilterResult::Reject)
@@ -51,12 +51,12 @@ impl CooldownFilter {
                 let mut key = platform.clone();
                 
                 if self.per_channel {
-                    key.push_str(":");
+                    key.push(':');
                     key.push_str(channel);
                 }
                 
                 if self.per_user {
-                    key.push_str(":");
+                    key.push(':');
                     key.push_str(user);
                 }
                 
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::Error;
+use crate::eventbus::BotEvent;
+use crate::services::event_context::EventContext;
+use crate::services::event_pipeline::{EventFilter, FilterResult};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DonationAmountFilterConfig {
+    #[serde(default)]
+    min_amount: f64,
+    #[serde(default)]
+    max_amount: Option<f64>,
+}
+
+/// Filter donations by amount in the base currency, e.g. for alert tiers.
+/// Donations that couldn't be converted only pass when there is no minimum.
+pub struct DonationAmountFilter {
+    min_amount: f64,
+    max_amount: Option<f64>,
+}
+
+impl DonationAmountFilter {
+    pub fn new(min_amount: f64, max_amount: Option<f64>) -> Self {
+        Self {
+            min_amount,
+            max_amount,
+        }
+    }
+}
+
+#[async_trait]
+impl EventFilter for DonationAmountFilter {
+    fn id(&self) -> &str {
+        "donation_amount_filter"
+    }
+
+    fn name(&self) -> &str {
+        "Donation Amount Filter"
+    }
+
+    fn configure(&mut self, config: serde_json::Value) -> Result<(), Error> {
+        let config: DonationAmountFilterConfig = serde_json::from_value(config)
+            .map_err(|e| Error::Platform(format!("Invalid donation amount filter config: {}", e)))?;
+
+        self.min_amount = config.min_amount;
+        self.max_amount = config.max_amount;
+        Ok(())
+    }
+
+    async fn apply(&self, event: &BotEvent, _context: &EventContext) -> Result<FilterResult, Error> {
+        match event {
+            BotEvent::Donation(donation) => {
+                let passes = match donation.amount_base {
+                    Some(amount) => amount >= self.min_amount
+                        && self.max_amount.map(|max| amount < max).unwrap_or(true),
+                    None => self.min_amount <= 0.0,
+                };
+                if passes {
+                    Ok(FilterResult::Pass)
+                } else {
+                    Ok(FilterResult::Reject)
+                }
+            }
+            _ => Ok(FilterResult::Reject),
+        }
+    }
+}
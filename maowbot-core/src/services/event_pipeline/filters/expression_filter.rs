@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::Error;
+use crate::eventbus::BotEvent;
+use crate::services::event_context::EventContext;
+use crate::services::event_pipeline::{Condition, EventFilter, FilterResult};
+use crate::services::event_pipeline::variables::event_variables;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExpressionFilterConfig {
+    #[serde(rename = "if")]
+    condition: String,
+}
+
+/// Filter on a condition over the event's variables, e.g. `bits >= 1000`
+pub struct ExpressionFilter {
+    condition: Option<Condition>,
+}
+
+impl ExpressionFilter {
+    pub fn new() -> Self {
+        Self { condition: None }
+    }
+}
+
+impl Default for ExpressionFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventFilter for ExpressionFilter {
+    fn id(&self) -> &str {
+        "expression_filter"
+    }
+
+    fn name(&self) -> &str {
+        "Expression Filter"
+    }
+
+    fn configure(&mut self, config: serde_json::Value) -> Result<(), Error> {
+        let config: ExpressionFilterConfig = serde_json::from_value(config)
+            .map_err(|e| Error::Platform(format!("Invalid expression filter config: {}", e)))?;
+
+        self.condition = Some(Condition::parse(&config.condition)?);
+        Ok(())
+    }
+
+    async fn apply(&self, event: &BotEvent, _context: &EventContext) -> Result<FilterResult, Error> {
+        let condition = self.condition.as_ref()
+            .ok_or_else(|| Error::Platform("Expression filter has no condition".into()))?;
+        let variables = event_variables(event).into_iter().collect();
+        if condition.evaluate(&variables) {
+            Ok(FilterResult::Pass)
+        } else {
+            Ok(FilterResult::Reject)
+        }
+    }
+}
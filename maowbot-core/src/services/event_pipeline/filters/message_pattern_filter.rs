@@ -29,7 +29,7 @@ impl MessagePatternFilter {
     pub fn new(patterns: Vec<&str>, match_any: bool) -> Result<Self, Error> {
         let compiled_patterns = patterns
             .into_iter()
-            .map(|p| Regex::new(p))
+            .map(Regex::new)
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| Error::Platform(format!("Invalid regex pattern: {}", e)))?;
         
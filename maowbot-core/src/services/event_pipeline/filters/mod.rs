@@ -6,6 +6,8 @@ mod message_pattern_filter;
 mod message_length_filter;
 mod time_window_filter;
 mod cooldown_filter;
+mod donation_amount_filter;
+mod expression_filter;
 
 pub use platform_filter::PlatformFilter;
 pub use channel_filter::ChannelFilter;
@@ -14,4 +16,6 @@ pub use user_level_filter::UserLevelFilter;
 pub use message_pattern_filter::MessagePatternFilter;
 pub use message_length_filter::MessageLengthFilter;
 pub use time_window_filter::TimeWindowFilter;
-pub use cooldown_filter::CooldownFilter;
\ No newline at end of file
+pub use cooldown_filter::CooldownFilter;
+pub use donation_amount_filter::DonationAmountFilter;
+pub use expression_filter::ExpressionFilter;
\ No newline at end of file
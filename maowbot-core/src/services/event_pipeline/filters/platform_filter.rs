@@ -48,6 +48,7 @@ impl EventFilter for PlatformFilter {
         let platform = match event {
             BotEvent::ChatMessage { platform, .. } => Platform::from_string(platform),
             BotEvent::TwitchEventSub(_) => Platform::TwitchEventSub,
+            BotEvent::Kick(_) => Platform::Kick,
             _ => return Ok(FilterResult::Reject),
         };
 
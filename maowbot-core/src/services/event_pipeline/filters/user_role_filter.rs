@@ -54,7 +54,7 @@ impl EventFilter for UserRoleFilter {
         match event {
             BotEvent::ChatMessage { platform, user, .. } => {
                 // Get user from database
-                let user_record = context.user_service
+                let _user_record = context.user_service
                     .get_or_create_user(platform, user, None)
                     .await?;
                 
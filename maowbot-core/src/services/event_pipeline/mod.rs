@@ -5,8 +5,12 @@ pub mod builder;
 pub mod examples;
 pub mod filters;
 pub mod actions;
+pub mod condition;
+pub mod throttle;
+pub mod variables;
 
-pub use action::{EventAction, ActionResult, ActionContext};
+pub use action::{EventAction, ActionResult, ActionContext, PipelineFlow};
+pub use condition::Condition;
 pub use filter::{EventFilter, FilterResult};
 pub use pipeline::{EventPipeline, PipelineExecutor, PipelineEventService};
 pub use builder::PipelineBuilder;
\ No newline at end of file
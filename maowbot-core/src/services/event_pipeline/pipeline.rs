@@ -1,12 +1,11 @@
 use std::sync::Arc;
-use async_trait::async_trait;
 use tracing::{debug, info, warn, error};
 use crate::Error;
 use crate::eventbus::BotEvent;
 use crate::services::event_context::EventContext;
 use crate::services::event_pipeline::{
     EventFilter, FilterResult,
-    EventAction, ActionResult, ActionContext,
+    EventAction, ActionResult, ActionContext, PipelineFlow,
 };
 
 /// A complete event processing pipeline
@@ -89,6 +88,12 @@ impl EventPipeline {
                     return Err(e);
                 }
             }
+            // In-code pipelines have no action orders, so only a stop is honoured
+            if action_context.flow == PipelineFlow::Stop {
+                info!("Pipeline {}: Action {} stopped the pipeline", self.id, action.id());
+                completed = true;
+                break;
+            }
         }
 
         Ok(completed || self.stop_on_match)
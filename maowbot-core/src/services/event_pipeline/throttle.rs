@@ -0,0 +1,173 @@
+// File: maowbot-core/src/services/event_pipeline/throttle.rs
+//
+// Deciding whether a pipeline may run again under its `PipelineThrottle`:
+// a cooldown and a max-runs-per-window, each for the pipeline as a whole and
+// per user. The runs themselves are counted in the database by the
+// executor, so this only judges the usage it is handed.
+
+use std::fmt;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use maowbot_common::models::event_pipeline::{PipelineThrottle, ThrottleUsage};
+use crate::eventbus::BotEvent;
+use crate::services::event_pipeline::variables::event_variables;
+
+/// The throttle scope for runs of the whole pipeline.
+pub const PIPELINE_SCOPE: &str = "";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleScope {
+    Pipeline,
+    User,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThrottleVerdict {
+    Allow,
+    Cooldown { scope: ThrottleScope, remaining: Duration },
+    RateLimited { scope: ThrottleScope, max: i32, window_seconds: i32 },
+}
+
+impl fmt::Display for ThrottleVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let who = |scope: &ThrottleScope| match scope {
+            ThrottleScope::Pipeline => "pipeline",
+            ThrottleScope::User => "user",
+        };
+        match self {
+            ThrottleVerdict::Allow => write!(f, "allowed"),
+            ThrottleVerdict::Cooldown { scope, remaining } => {
+                write!(f, "{} cooldown, {}s left", who(scope), remaining.num_seconds().max(1))
+            }
+            ThrottleVerdict::RateLimited { scope, max, window_seconds } => {
+                write!(f, "{} limit of {} runs per {}s reached", who(scope), max, window_seconds)
+            }
+        }
+    }
+}
+
+/// Who an event is from, as a throttle scope: `<platform>:<user id or name>`.
+/// None for events without a user (those only count against the pipeline).
+pub fn user_scope_key(event: &BotEvent) -> Option<String> {
+    let variables = event_variables(event);
+    let text = |key: &str| variables.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_lowercase);
+    let user = text("user_id").or_else(|| text("user"))?;
+    Some(format!("{}:{}", text("platform").unwrap_or_default(), user))
+}
+
+fn check_scope(
+    scope: ThrottleScope,
+    cooldown_seconds: i32,
+    max: i32,
+    window_seconds: i32,
+    usage: &ThrottleUsage,
+    now: DateTime<Utc>,
+) -> ThrottleVerdict {
+    if cooldown_seconds > 0 {
+        if let Some(last) = usage.last_hit {
+            let ready_at = last + Duration::seconds(i64::from(cooldown_seconds));
+            if ready_at > now {
+                return ThrottleVerdict::Cooldown { scope, remaining: ready_at - now };
+            }
+        }
+    }
+    if max > 0 && window_seconds > 0 && usage.in_window >= i64::from(max) {
+        return ThrottleVerdict::RateLimited { scope, max, window_seconds };
+    }
+    ThrottleVerdict::Allow
+}
+
+/// Judges a run against the pipeline-wide usage and, when the event has a
+/// user, that user's usage. Usage counts runs since `window_seconds` ago.
+pub fn judge(
+    throttle: &PipelineThrottle,
+    now: DateTime<Utc>,
+    pipeline: &ThrottleUsage,
+    user: Option<&ThrottleUsage>,
+) -> ThrottleVerdict {
+    let verdict = check_scope(
+        ThrottleScope::Pipeline,
+        throttle.cooldown_seconds,
+        throttle.max_executions,
+        throttle.window_seconds,
+        pipeline,
+        now,
+    );
+    if verdict != ThrottleVerdict::Allow {
+        return verdict;
+    }
+    match user {
+        Some(usage) => check_scope(
+            ThrottleScope::User,
+            throttle.user_cooldown_seconds,
+            throttle.max_executions_per_user,
+            throttle.window_seconds,
+            usage,
+            now,
+        ),
+        None => ThrottleVerdict::Allow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle() -> PipelineThrottle {
+        PipelineThrottle {
+            cooldown_seconds: 10,
+            user_cooldown_seconds: 60,
+            max_executions: 5,
+            max_executions_per_user: 2,
+            window_seconds: 300,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_applies_cooldowns_then_windows() {
+        let now = Utc::now();
+        let usage = |in_window, ago: Option<i64>| ThrottleUsage {
+            in_window,
+            last_hit: ago.map(|s| now - Duration::seconds(s)),
+        };
+        let t = throttle();
+
+        assert_eq!(judge(&t, now, &usage(0, None), None), ThrottleVerdict::Allow);
+        assert!(matches!(
+            judge(&t, now, &usage(1, Some(4)), None),
+            ThrottleVerdict::Cooldown { scope: ThrottleScope::Pipeline, .. }
+        ));
+        assert!(matches!(
+            judge(&t, now, &usage(5, Some(30)), None),
+            ThrottleVerdict::RateLimited { scope: ThrottleScope::Pipeline, max: 5, .. }
+        ));
+        assert!(matches!(
+            judge(&t, now, &usage(1, Some(30)), Some(&usage(1, Some(30)))),
+            ThrottleVerdict::Cooldown { scope: ThrottleScope::User, .. }
+        ));
+        assert!(matches!(
+            judge(&t, now, &usage(1, Some(30)), Some(&usage(2, Some(120)))),
+            ThrottleVerdict::RateLimited { scope: ThrottleScope::User, max: 2, .. }
+        ));
+        assert_eq!(judge(&t, now, &usage(1, Some(30)), Some(&usage(1, Some(120)))), ThrottleVerdict::Allow);
+    }
+
+    #[test]
+    fn test_keys_users_by_platform() {
+        let event = BotEvent::ChatMessage {
+            platform: "twitch-irc".into(),
+            channel: "#kittyn".into(),
+            user: "Mochi".into(),
+            text: "hi".into(),
+            timestamp: Utc::now(),
+            metadata: Default::default(),
+        };
+        assert_eq!(user_scope_key(&event).as_deref(), Some("twitch-irc:mochi"));
+        assert_eq!(user_scope_key(&BotEvent::Tick), None);
+    }
+}
@@ -0,0 +1,215 @@
+// File: maowbot-core/src/services/event_pipeline/variables.rs
+//
+// The variables a pipeline starts with, taken from the event that triggered
+// it: `user`, `channel`, `message`, `bits`, `amount`, `reward`, ... plus
+// `event_type` and `platform` on every event. They seed the action context's
+// shared data, so conditions (`bits >= 1000`) and action templates
+// (`{user}`) can use them, and actions add their own as they run.
+
+use serde_json::{Map, Value};
+use crate::eventbus::{BotEvent, KickEventData, TwitchEventSubData};
+
+/// The variables for `event`. Every event has `event_type` and, when it has
+/// one, `platform`.
+pub fn event_variables(event: &BotEvent) -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert("event_type".into(), event.event_type().into());
+    if let Some(platform) = event.platform() {
+        fields.insert("platform".into(), platform.to_string().into());
+    }
+    let mut set = |key: &str, value: Value| {
+        fields.insert(key.to_string(), value);
+    };
+    match event {
+        BotEvent::ChatMessage { platform, channel, user, text, timestamp, .. } => {
+            set("platform", platform.as_str().into());
+            set("channel", channel.as_str().into());
+            set("user", user.as_str().into());
+            set("message", text.as_str().into());
+            set("timestamp", timestamp.to_rfc3339().into());
+        }
+        BotEvent::SystemMessage(text) => set("message", text.as_str().into()),
+        BotEvent::TwitchEventSub(data) => match data {
+            TwitchEventSubData::StreamOnline(evt) => {
+                set("user", evt.broadcaster_user_name.as_str().into());
+                set("channel", evt.broadcaster_user_login.as_str().into());
+                set("started_at", evt.started_at.to_rfc3339().into());
+            }
+            TwitchEventSubData::ChannelUpdate(evt) => {
+                set("user", evt.broadcaster_user_name.as_str().into());
+                set("channel", evt.broadcaster_user_login.as_str().into());
+                set("title", evt.title.as_str().into());
+                set("category", evt.category_name.as_str().into());
+            }
+            TwitchEventSubData::ChannelFollow(evt) => {
+                set("user", evt.user_name.as_str().into());
+                set("user_id", evt.user_id.as_str().into());
+                set("channel", evt.broadcaster_user_login.as_str().into());
+            }
+            TwitchEventSubData::ChannelSubscribe(evt) => {
+                set("user", evt.user_name.as_str().into());
+                set("user_id", evt.user_id.as_str().into());
+                set("channel", evt.broadcaster_user_login.as_str().into());
+                set("tier", evt.tier.as_str().into());
+                set("is_gift", evt.is_gift.into());
+            }
+            TwitchEventSubData::ChannelBitsUse(evt) => {
+                set("user", evt.user_name.as_str().into());
+                set("user_id", evt.user_id.as_str().into());
+                set("channel", evt.broadcaster_user_login.as_str().into());
+                set("bits", evt.bits.into());
+                set("amount", evt.bits.into());
+            }
+            TwitchEventSubData::ChannelRaid(evt) => {
+                set("user", evt.from_broadcaster_user_name.as_str().into());
+                set("user_id", evt.from_broadcaster_user_id.as_str().into());
+                set("channel", evt.to_broadcaster_user_login.as_str().into());
+                set("viewers", evt.viewers.into());
+            }
+            TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(evt) => {
+                set("user", evt.user_name.as_str().into());
+                set("user_id", evt.user_id.as_str().into());
+                set("channel", evt.broadcaster_user_login.as_str().into());
+                set("reward", evt.reward.title.as_str().into());
+                set("cost", evt.reward.cost.into());
+                set("message", evt.user_input.as_str().into());
+            }
+            _ => {}
+        },
+        BotEvent::Kick(data) => {
+            set("user", data.username().into());
+            match data {
+                KickEventData::Subscription(sub) => set("months", sub.months.into()),
+                KickEventData::GiftedSubscriptions(gift) => set("count", gift.gifted_usernames.len().into()),
+                KickEventData::Follow(_) => {}
+            }
+        }
+        BotEvent::Donation(donation) => {
+            set("platform", donation.source.to_string().into());
+            set("user", donation.donor_name.as_str().into());
+            set("amount", donation.amount.into());
+            set("currency", donation.currency.as_str().into());
+            set("formatted_amount", donation.formatted_amount().into());
+            set("message", donation.message.as_deref().unwrap_or_default().into());
+        }
+        BotEvent::ViewerMilestone(milestone) => {
+            set("channel", milestone.channel.as_str().into());
+            set("user", milestone.display_name.as_str().into());
+            set("user_id", milestone.twitch_user_id.as_str().into());
+            set("kind", milestone.kind.to_string().into());
+            set("value", milestone.value.into());
+            set("unit", milestone.kind.unit().into());
+        }
+        BotEvent::ObsSceneChanged { instance, scene, .. } => {
+            set("instance", (*instance).into());
+            set("scene", scene.as_str().into());
+        }
+        BotEvent::HeartRate { source, bpm, .. } => {
+            set("source", source.as_str().into());
+            set("bpm", (*bpm).into());
+        }
+        BotEvent::CredentialRefreshFailed { platform, user_name, expires_at, error, .. } => {
+            set("platform", platform.as_str().into());
+            set("user", user_name.as_str().into());
+            set("expires_at", expires_at.map(|t| t.to_rfc3339()).unwrap_or_default().into());
+            set("error", error.as_str().into());
+        }
+        BotEvent::PlatformConnectionChanged { platform, account_name, state, attempt, error, .. } => {
+            set("platform", platform.as_str().into());
+            set("user", account_name.as_str().into());
+            set("state", state.as_str().into());
+            set("attempt", (*attempt).into());
+            set("error", error.as_deref().unwrap_or_default().into());
+        }
+        BotEvent::ConfigChanged { key, old_value, new_value, .. } => {
+            set("key", key.as_str().into());
+            set("old_value", old_value.as_deref().unwrap_or_default().into());
+            set("new_value", new_value.as_deref().unwrap_or_default().into());
+        }
+        BotEvent::VRChatPresence { joined, user_id, display_name, location, .. } => {
+            set("user", display_name.as_str().into());
+            set("user_id", user_id.as_str().into());
+            set("joined", (*joined).into());
+            set("location", location.as_str().into());
+        }
+        BotEvent::VRChatGroup { group_id, user_id, display_name, title, .. } => {
+            set("group_id", group_id.as_str().into());
+            set("user", display_name.as_deref().unwrap_or_default().into());
+            set("user_id", user_id.as_deref().unwrap_or_default().into());
+            set("title", title.as_deref().unwrap_or_default().into());
+        }
+        BotEvent::TwitchChatters { channel, joined, left, present, .. } => {
+            let names = |chatters: &[crate::platforms::twitch::requests::chatters::Chatter]| {
+                chatters.iter().map(|c| c.user_name.as_str()).collect::<Vec<_>>().join(", ")
+            };
+            set("channel", channel.as_str().into());
+            set("joined", names(joined).into());
+            set("left", names(left).into());
+            set("joined_count", joined.len().into());
+            set("left_count", left.len().into());
+            set("count", (*present).into());
+        }
+        BotEvent::TwitchScheduleStartingSoon { channel, segment_id, title, category, start_time, timestamp } => {
+            set("channel", channel.as_str().into());
+            set("segment_id", segment_id.as_str().into());
+            set("title", title.as_str().into());
+            set("category", category.as_str().into());
+            set("start_time", start_time.to_rfc3339().into());
+            set("minutes", (*start_time - *timestamp).num_minutes().max(0).into());
+        }
+        BotEvent::HypeMoment { channel, kind, score, messages, emote_messages, bits, subs, .. } => {
+            set("channel", channel.as_str().into());
+            set("kind", kind.as_str().into());
+            set("score", ((*score * 100.0).round() / 100.0).into());
+            set("messages", (*messages).into());
+            set("emote_messages", (*emote_messages).into());
+            set("bits", (*bits).into());
+            set("subs", (*subs).into());
+        }
+        BotEvent::ResponderTriggered { platform, channel, user, platform_user_id, responder, matched, text, response, .. } => {
+            set("platform", platform.as_str().into());
+            set("channel", channel.as_str().into());
+            set("user", user.as_str().into());
+            set("user_id", platform_user_id.as_str().into());
+            set("responder", responder.as_str().into());
+            set("match", matched.as_str().into());
+            set("message", text.as_str().into());
+            set("response", response.as_deref().unwrap_or_default().into());
+        }
+        BotEvent::RedeemApproval { approval_id, status, reward_name, channel, user, user_input, decided_by, .. } => {
+            set("approval_id", approval_id.to_string().into());
+            set("status", status.to_string().into());
+            set("reward", reward_name.as_str().into());
+            set("channel", channel.as_str().into());
+            set("user", user.as_str().into());
+            set("input", user_input.as_str().into());
+            set("decided_by", decided_by.as_deref().unwrap_or_default().into());
+        }
+        BotEvent::ModerationEscalated { user_id, user, channel, category, rule, offense, action, .. } => {
+            set("user_id", user_id.to_string().into());
+            set("user", user.as_str().into());
+            set("channel", channel.as_str().into());
+            set("category", category.as_str().into());
+            set("rule", rule.as_str().into());
+            set("offense", (*offense).into());
+            set("action", action.as_str().into());
+        }
+        BotEvent::OpsAlert { kind, severity, key, title, message, .. } => {
+            set("kind", kind.to_string().into());
+            set("severity", severity.to_string().into());
+            set("key", key.as_str().into());
+            set("title", title.as_str().into());
+            set("message", message.as_str().into());
+        }
+        BotEvent::CrowdVoteDecided { channel, title, winner, control, weight, voters, .. } => {
+            set("channel", channel.as_str().into());
+            set("title", title.as_str().into());
+            set("winner", winner.as_str().into());
+            set("control", control.as_deref().unwrap_or_default().into());
+            set("weight", (*weight).into());
+            set("voters", (*voters).into());
+        }
+        BotEvent::Tick => {}
+    }
+    fields
+}
@@ -8,43 +8,95 @@ use uuid::Uuid;
 use crate::Error;
 use crate::eventbus::{EventBus, BotEvent};
 use crate::services::event_context::EventContext;
-use crate::repositories::postgres::event_pipeline::PostgresEventPipelineRepository;
 
 use maowbot_common::models::event_pipeline::{
     EventPipeline as DbPipeline, PipelineFilter as DbFilter, PipelineAction as DbAction,
-    PipelineExecutionLog, PipelineExecutionStatus, ActionExecutionResult, ActionExecutionStatus,
-};
-use maowbot_common::traits::event_pipeline_traits::{
-    EventPipelineRepository, PipelineExecutionLogRepository, PipelineSharedDataRepository,
-    EventTypeRegistryRepository, EventHandlerRegistryRepository,
+    PipelineExecutionStatus, PipelineThrottle, ThrottleUsage,
 };
+use maowbot_common::traits::event_pipeline_traits::EventPipelineSystemRepository;
 
 // Import our filter and action traits
-use super::event_pipeline::{EventFilter, FilterResult, EventAction, ActionResult, ActionContext};
+use super::event_pipeline::{EventFilter, FilterResult, EventAction, ActionResult, ActionContext, Condition, PipelineFlow};
+use super::event_pipeline::variables::event_variables;
+use super::event_pipeline::throttle::{judge, user_scope_key, ThrottleVerdict, PIPELINE_SCOPE};
 
 // Import built-in implementations (to be created)
 use super::event_pipeline::filters::*;
 use super::event_pipeline::actions::*;
 
+/// Builds a fresh filter or action of one registered type
+type FilterFactory = Box<dyn Fn() -> Box<dyn EventFilter> + Send + Sync>;
+type ActionFactory = Box<dyn Fn() -> Box<dyn EventAction> + Send + Sync>;
+
 /// Service that manages and executes database-driven event pipelines
 pub struct EventPipelineService {
     event_bus: Arc<EventBus>,
     context: Arc<EventContext>,
-    pub repository: Arc<PostgresEventPipelineRepository>,
+    pub repository: Arc<dyn EventPipelineSystemRepository>,
     
     // Cache of loaded pipelines
-    pub pipelines: Arc<RwLock<Vec<LoadedPipeline>>>,
+    pipelines: Arc<RwLock<Vec<LoadedPipeline>>>,
     
     // Registry of available filter/action types
-    filter_registry: Arc<RwLock<HashMap<String, Box<dyn Fn() -> Box<dyn EventFilter> + Send + Sync>>>>,
-    action_registry: Arc<RwLock<HashMap<String, Box<dyn Fn() -> Box<dyn EventAction> + Send + Sync>>>>,
+    filter_registry: Arc<RwLock<HashMap<String, FilterFactory>>>,
+    action_registry: Arc<RwLock<HashMap<String, ActionFactory>>>,
+}
+
+/// Result of running a single pipeline against one event
+#[derive(Debug, Clone)]
+pub struct PipelineOutcome {
+    pub execution_id: Uuid,
+    pub filters_passed: bool,
+    pub failed: bool,
+}
+
+/// When an action runs, from its `condition_type` and `condition_config`
+enum ActionCondition {
+    Always,
+    PreviousSuccess,
+    PreviousFailure,
+    /// `expression` ({"if": "bits >= 1000"}) or `data_check` ({"key": "name"})
+    Expression(Condition),
+}
+
+impl ActionCondition {
+    fn from_action(action: &DbAction) -> Result<Self, Error> {
+        let config = action.condition_config.as_ref();
+        let field = |name: &str| {
+            config.and_then(|c| c.get(name)).and_then(|v| v.as_str())
+                .ok_or_else(|| Error::Parse(format!(
+                    "{} condition of action {} needs \"{}\" in its config",
+                    action.condition_type.as_deref().unwrap_or_default(), action.action_id, name
+                )))
+        };
+        match action.condition_type.as_deref() {
+            None => Ok(ActionCondition::Always),
+            Some("previous_success") => Ok(ActionCondition::PreviousSuccess),
+            Some("previous_failure") => Ok(ActionCondition::PreviousFailure),
+            Some("expression") => Ok(ActionCondition::Expression(Condition::parse(field("if")?)?)),
+            Some("data_check") => Ok(ActionCondition::Expression(Condition::parse(field("key")?)?)),
+            Some(other) => Err(Error::Parse(format!("Unknown condition type '{}'", other))),
+        }
+    }
+
+    /// `previous_ok` is None before the first action that ran.
+    fn allows(&self, previous_ok: Option<bool>, variables: &HashMap<String, serde_json::Value>) -> bool {
+        match self {
+            ActionCondition::Always => true,
+            ActionCondition::PreviousSuccess => previous_ok.unwrap_or(true),
+            ActionCondition::PreviousFailure => previous_ok == Some(false),
+            ActionCondition::Expression(condition) => condition.evaluate(variables),
+        }
+    }
 }
 
 /// A pipeline loaded from the database with instantiated filters and actions
 struct LoadedPipeline {
     pub pipeline: DbPipeline,
     pub filters: Vec<(DbFilter, Box<dyn EventFilter>)>,
-    pub actions: Vec<(DbAction, Box<dyn EventAction>)>,
+    pub actions: Vec<(DbAction, Box<dyn EventAction>, ActionCondition)>,
+    /// Only set when one of its limits is on
+    pub throttle: Option<PipelineThrottle>,
 }
 
 impl EventPipelineService {
@@ -56,7 +108,7 @@ impl EventPipelineService {
     pub async fn new(
         event_bus: Arc<EventBus>,
         context: Arc<EventContext>,
-        repository: Arc<PostgresEventPipelineRepository>,
+        repository: Arc<dyn EventPipelineSystemRepository>,
     ) -> Result<Self, Error> {
         let service = Self {
             event_bus,
@@ -98,6 +150,10 @@ impl EventPipelineService {
             Box::new(|| Box::new(TimeWindowFilter::new(0, 23, "UTC".to_string())) as Box<dyn EventFilter>));
         filters.insert("cooldown_filter".to_string(),
             Box::new(|| Box::new(CooldownFilter::new(60, true)) as Box<dyn EventFilter>));
+        filters.insert("donation_amount_filter".to_string(),
+            Box::new(|| Box::new(DonationAmountFilter::new(0.0, None)) as Box<dyn EventFilter>));
+        filters.insert("expression_filter".to_string(),
+            Box::new(|| Box::new(ExpressionFilter::new()) as Box<dyn EventFilter>));
         
         // Register actions
         actions.insert("log_action".to_string(),
@@ -122,6 +178,14 @@ impl EventPipelineService {
             Box::new(|| Box::new(PluginCallAction::new()) as Box<dyn EventAction>));
         actions.insert("ai_respond".to_string(),
             Box::new(|| Box::new(AiRespondAction::new()) as Box<dyn EventAction>));
+        actions.insert("post_status".to_string(),
+            Box::new(|| Box::new(PostStatusAction::new()) as Box<dyn EventAction>));
+        actions.insert("http_request".to_string(),
+            Box::new(|| Box::new(HttpRequestAction::new()) as Box<dyn EventAction>));
+        actions.insert("branch".to_string(),
+            Box::new(|| Box::new(BranchAction::new()) as Box<dyn EventAction>));
+        actions.insert("stop".to_string(),
+            Box::new(|| Box::new(StopAction::new()) as Box<dyn EventAction>));
         
         info!("Registered {} built-in filters and {} built-in actions", 
               filters.len(), actions.len());
@@ -177,8 +241,12 @@ impl EventPipelineService {
         let mut actions = Vec::new();
         
         for db_action in db_actions {
-            match self.instantiate_action(&db_action).await {
-                Ok(action) => actions.push((db_action, action)),
+            let instantiated = match ActionCondition::from_action(&db_action) {
+                Ok(condition) => self.instantiate_action(&db_action).await.map(|a| (a, condition)),
+                Err(e) => Err(e),
+            };
+            match instantiated {
+                Ok((action, condition)) => actions.push((db_action, action, condition)),
                 Err(e) => {
                     error!("Failed to instantiate action {} for pipeline {}: {:?}", 
                            db_action.action_type, pipeline.name, e);
@@ -187,10 +255,22 @@ impl EventPipelineService {
             }
         }
         
+        let throttle = self.repository.get_throttle(pipeline_id).await?
+            .filter(PipelineThrottle::is_active);
+        if let Some(throttle) = &throttle {
+            // Runs older than the longest look-back no longer matter
+            let horizon = throttle.pipeline_horizon().max(throttle.user_horizon());
+            let older_than = Utc::now() - chrono::Duration::seconds(i64::from(horizon));
+            if let Err(e) = self.repository.prune_throttle_hits(pipeline_id, older_than).await {
+                warn!("Failed to prune throttle hits for pipeline {}: {:?}", pipeline.name, e);
+            }
+        }
+        
         Ok(LoadedPipeline {
             pipeline: pipeline.clone(),
             filters,
             actions,
+            throttle,
         })
     }
     
@@ -245,172 +325,309 @@ impl EventPipelineService {
         event: BotEvent,
         pipelines: Arc<RwLock<Vec<LoadedPipeline>>>,
         context: Arc<EventContext>,
-        repository: Arc<PostgresEventPipelineRepository>,
+        repository: Arc<dyn EventPipelineSystemRepository>,
     ) -> Result<(), Error> {
         let event_type = event.event_type();
         let platform = event.platform().map(|p| p.to_string()).unwrap_or_default();
         
         trace!("Processing event {} from platform {} through pipelines", event_type, platform);
+        let started = std::time::Instant::now();
         
         let pipelines = pipelines.read().await;
+        let mut executed = 0usize;
         
         for loaded_pipeline in pipelines.iter() {
             if !loaded_pipeline.pipeline.enabled {
                 continue;
             }
             
-            // Create execution log
-            let execution_id = match repository.create_execution(
-                loaded_pipeline.pipeline.pipeline_id,
-                &event_type,
-                serde_json::json!({
-                    "event_type": event_type,
-                    "platform": platform
-                })
-            ).await {
-                Ok(log) => log.execution_id,
-                Err(e) => {
-                    error!("Failed to create execution log: {:?}", e);
-                    continue;
-                }
+            let outcome = match Self::execute_pipeline(loaded_pipeline, &event, &context, &repository, true).await {
+                Some(outcome) => outcome,
+                None => continue,
             };
+            if outcome.filters_passed {
+                executed += 1;
+            }
             
-            // Check filters
-            let mut all_filters_pass = true;
-            for (db_filter, filter) in &loaded_pipeline.filters {
-                match filter.apply(&event, &context).await {
-                    Ok(FilterResult::Pass) => {
-                        trace!("Pipeline {}: Filter {} passed", 
-                               loaded_pipeline.pipeline.name, db_filter.filter_type);
-                    }
-                    Ok(FilterResult::Reject) => {
-                        trace!("Pipeline {}: Filter {} rejected", 
-                               loaded_pipeline.pipeline.name, db_filter.filter_type);
-                        all_filters_pass = false;
-                        break;
-                    }
-                    Err(e) => {
-                        error!("Pipeline {}: Filter {} error: {:?}", 
-                               loaded_pipeline.pipeline.name, db_filter.filter_type, e);
-                        all_filters_pass = false;
-                        break;
-                    }
+            // Check if we should stop processing other pipelines
+            if loaded_pipeline.pipeline.stop_on_match && outcome.filters_passed && !outcome.failed {
+                info!("Pipeline {} executed with stop_on_match, skipping remaining pipelines", 
+                      loaded_pipeline.pipeline.name);
+                break;
+            }
+        }
+
+        debug!(
+            event_type = %event_type,
+            platform = %platform,
+            pipelines_executed = executed,
+            latency_ms = started.elapsed().as_millis() as u64,
+            "Event processed through pipelines"
+        );
+        
+        Ok(())
+    }
+    
+    /// Run a single loaded pipeline against an event, recording an execution log.
+    /// Returns `None` if the execution log could not be created.
+    async fn execute_pipeline(
+        loaded_pipeline: &LoadedPipeline,
+        event: &BotEvent,
+        context: &Arc<EventContext>,
+        repository: &Arc<dyn EventPipelineSystemRepository>,
+        apply_throttle: bool,
+    ) -> Option<PipelineOutcome> {
+        let event_type = event.event_type();
+        let platform = event.platform().map(|p| p.to_string()).unwrap_or_default();
+        
+        // Create execution log
+        let execution_id = match repository.create_execution(
+            loaded_pipeline.pipeline.pipeline_id,
+            &event_type,
+            serde_json::json!({
+                "event_type": event_type,
+                "platform": platform
+            })
+        ).await {
+            Ok(log) => log.execution_id,
+            Err(e) => {
+                error!("Failed to create execution log: {:?}", e);
+                return None;
+            }
+        };
+        
+        // Check filters
+        let mut all_filters_pass = true;
+        for (db_filter, filter) in &loaded_pipeline.filters {
+            match filter.apply(event, context).await {
+                Ok(FilterResult::Pass) => {
+                    trace!("Pipeline {}: Filter {} passed", 
+                           loaded_pipeline.pipeline.name, db_filter.filter_type);
+                }
+                Ok(FilterResult::Reject) => {
+                    trace!("Pipeline {}: Filter {} rejected", 
+                           loaded_pipeline.pipeline.name, db_filter.filter_type);
+                    all_filters_pass = false;
+                    break;
+                }
+                Err(e) => {
+                    error!("Pipeline {}: Filter {} error: {:?}", 
+                           loaded_pipeline.pipeline.name, db_filter.filter_type, e);
+                    all_filters_pass = false;
+                    break;
                 }
             }
-            
-            if !all_filters_pass {
-                // Update execution as skipped
+        }
+        
+        if !all_filters_pass {
+            // Update execution as skipped
+            let _ = repository.update_execution_status(
+                execution_id,
+                PipelineExecutionStatus::Success,
+                Some("Filters did not match".to_string())
+            ).await;
+            return Some(PipelineOutcome { execution_id, filters_passed: false, failed: false });
+        }
+        
+        if let (true, Some(throttle)) = (apply_throttle, &loaded_pipeline.throttle) {
+            let verdict = match Self::take_throttle_slot(loaded_pipeline.pipeline.pipeline_id, throttle, event, repository).await {
+                Ok(verdict) => verdict,
+                Err(e) => {
+                    // Better to run than to silently drop events while the database hiccups
+                    warn!("Pipeline {}: throttle check failed, running anyway: {:?}", loaded_pipeline.pipeline.name, e);
+                    ThrottleVerdict::Allow
+                }
+            };
+            if verdict != ThrottleVerdict::Allow {
+                debug!("Pipeline {} throttled: {}", loaded_pipeline.pipeline.name, verdict);
                 let _ = repository.update_execution_status(
                     execution_id,
                     PipelineExecutionStatus::Success,
-                    Some("Filters did not match".to_string())
+                    Some(format!("Throttled: {}", verdict))
                 ).await;
+                return Some(PipelineOutcome { execution_id, filters_passed: false, failed: false });
+            }
+        }
+        
+        info!("Executing pipeline {} for event {}", loaded_pipeline.pipeline.name, event_type);
+        
+        // Execute actions
+        let mut action_context = ActionContext {
+            event: event.clone(),
+            context: context.clone(),
+            shared_data: event_variables(event).into_iter().collect(),
+            execution_id,
+            flow: PipelineFlow::Continue,
+        };
+        
+        let mut any_failed = false;
+        let mut previous_ok = None;
+        let actions = &loaded_pipeline.actions;
+        let mut index = 0;
+        while let Some((db_action, action, condition)) = actions.get(index) {
+            index += 1;
+            if !condition.allows(previous_ok, &action_context.shared_data) {
+                trace!("Pipeline {}: Action {} skipped by its condition",
+                       loaded_pipeline.pipeline.name, db_action.action_type);
                 continue;
             }
+            let action_start = Utc::now();
             
-            info!("Executing pipeline {} for event {}", loaded_pipeline.pipeline.name, event_type);
-            
-            // Execute actions
-            let mut action_context = ActionContext {
-                event: event.clone(),
-                context: context.clone(),
-                shared_data: HashMap::new(),
-                execution_id,
-            };
-            
-            let mut any_failed = false;
-            for (db_action, action) in &loaded_pipeline.actions {
-                let action_start = Utc::now();
-                
-                match action.execute(&mut action_context).await {
-                    Ok(ActionResult::Success(data)) => {
-                        trace!("Pipeline {}: Action {} succeeded", 
-                               loaded_pipeline.pipeline.name, db_action.action_type);
-                        
-                        // Record success
-                        let _ = repository.add_action_result(
-                            execution_id,
-                            serde_json::json!({
-                                "action_id": db_action.action_id,
-                                "action_type": db_action.action_type,
-                                "status": "success",
-                                "started_at": action_start,
-                                "completed_at": Utc::now(),
-                                "output": data,
-                            })
-                        ).await;
-                    }
-                    Ok(ActionResult::Error(msg)) => {
-                        error!("Pipeline {}: Action {} failed: {}", 
-                               loaded_pipeline.pipeline.name, db_action.action_type, msg);
-                        
-                        // Record failure
-                        let _ = repository.add_action_result(
-                            execution_id,
-                            serde_json::json!({
-                                "action_id": db_action.action_id,
-                                "action_type": db_action.action_type,
-                                "status": "failed",
-                                "started_at": action_start,
-                                "completed_at": Utc::now(),
-                                "error": msg,
-                            })
-                        ).await;
-                        
-                        if !db_action.continue_on_error {
-                            any_failed = true;
-                            break;
-                        }
+            match action.execute(&mut action_context).await {
+                Ok(ActionResult::Success(data)) => {
+                    trace!("Pipeline {}: Action {} succeeded", 
+                           loaded_pipeline.pipeline.name, db_action.action_type);
+                    previous_ok = Some(true);
+                    
+                    // Record success
+                    let _ = repository.add_action_result(
+                        execution_id,
+                        serde_json::json!({
+                            "action_id": db_action.action_id,
+                            "action_type": db_action.action_type,
+                            "status": "success",
+                            "started_at": action_start,
+                            "completed_at": Utc::now(),
+                            "output": data,
+                        })
+                    ).await;
+                }
+                Ok(ActionResult::Error(msg)) => {
+                    error!("Pipeline {}: Action {} failed: {}", 
+                           loaded_pipeline.pipeline.name, db_action.action_type, msg);
+                    previous_ok = Some(false);
+                    
+                    // Record failure
+                    let _ = repository.add_action_result(
+                        execution_id,
+                        serde_json::json!({
+                            "action_id": db_action.action_id,
+                            "action_type": db_action.action_type,
+                            "status": "failed",
+                            "started_at": action_start,
+                            "completed_at": Utc::now(),
+                            "error": msg,
+                        })
+                    ).await;
+                    
+                    if !db_action.continue_on_error {
+                        any_failed = true;
+                        break;
                     }
-                    Err(e) => {
-                        error!("Pipeline {}: Action {} error: {:?}", 
-                               loaded_pipeline.pipeline.name, db_action.action_type, e);
-                        
-                        // Record error
-                        let _ = repository.add_action_result(
-                            execution_id,
-                            serde_json::json!({
-                                "action_id": db_action.action_id,
-                                "action_type": db_action.action_type,
-                                "status": "failed",
-                                "started_at": action_start,
-                                "completed_at": Utc::now(),
-                                "error": format!("{:?}", e),
-                            })
-                        ).await;
-                        
-                        if !db_action.continue_on_error {
-                            any_failed = true;
-                            break;
-                        }
+                }
+                Err(e) => {
+                    error!("Pipeline {}: Action {} error: {:?}", 
+                           loaded_pipeline.pipeline.name, db_action.action_type, e);
+                    previous_ok = Some(false);
+                    
+                    // Record error
+                    let _ = repository.add_action_result(
+                        execution_id,
+                        serde_json::json!({
+                            "action_id": db_action.action_id,
+                            "action_type": db_action.action_type,
+                            "status": "failed",
+                            "started_at": action_start,
+                            "completed_at": Utc::now(),
+                            "error": format!("{:?}", e),
+                        })
+                    ).await;
+                    
+                    if !db_action.continue_on_error {
+                        any_failed = true;
+                        break;
                     }
                 }
             }
             
-            // Update execution status
-            let status = if any_failed {
-                PipelineExecutionStatus::Failed
-            } else {
-                PipelineExecutionStatus::Success
-            };
-            
-            let _ = repository.update_execution_status(execution_id, status, None).await;
-            
-            // Update pipeline stats
-            let _ = repository.increment_execution_stats(
-                loaded_pipeline.pipeline.pipeline_id,
-                !any_failed
-            ).await;
-            
-            // Check if we should stop processing other pipelines
-            if loaded_pipeline.pipeline.stop_on_match && !any_failed {
-                info!("Pipeline {} executed with stop_on_match, skipping remaining pipelines", 
-                      loaded_pipeline.pipeline.name);
-                break;
+            // A stop or branch action may have changed what runs next
+            match std::mem::take(&mut action_context.flow) {
+                PipelineFlow::Continue => {}
+                PipelineFlow::Stop => {
+                    debug!("Pipeline {} stopped by action {}",
+                           loaded_pipeline.pipeline.name, db_action.action_type);
+                    break;
+                }
+                PipelineFlow::GoTo(order) if order > db_action.action_order => {
+                    index = actions.iter()
+                        .position(|(a, _, _)| a.action_order >= order)
+                        .unwrap_or(actions.len());
+                }
+                PipelineFlow::GoTo(order) => {
+                    error!("Pipeline {}: action {} (order {}) can't jump back to order {}",
+                           loaded_pipeline.pipeline.name, db_action.action_type, db_action.action_order, order);
+                    any_failed = true;
+                    break;
+                }
             }
         }
         
-        Ok(())
+        // Update execution status
+        let status = if any_failed {
+            PipelineExecutionStatus::Failed
+        } else {
+            PipelineExecutionStatus::Success
+        };
+        
+        let _ = repository.update_execution_status(execution_id, status, None).await;
+        
+        // Update pipeline stats
+        let _ = repository.increment_execution_stats(
+            loaded_pipeline.pipeline.pipeline_id,
+            !any_failed
+        ).await;
+        
+        Some(PipelineOutcome { execution_id, filters_passed: true, failed: any_failed })
+    }
+    
+    /// Checks the pipeline's limits for `event` and, when it may run, counts
+    /// the run against the pipeline and the event's user.
+    async fn take_throttle_slot(
+        pipeline_id: Uuid,
+        throttle: &PipelineThrottle,
+        event: &BotEvent,
+        repository: &Arc<dyn EventPipelineSystemRepository>,
+    ) -> Result<ThrottleVerdict, Error> {
+        let now = Utc::now();
+        let window_since = now - chrono::Duration::seconds(i64::from(throttle.window_seconds));
+        let user_key = user_scope_key(event)
+            .filter(|_| throttle.user_horizon() > 0);
+        
+        let pipeline_usage = if throttle.pipeline_horizon() > 0 {
+            repository.throttle_usage(pipeline_id, PIPELINE_SCOPE, window_since).await?
+        } else {
+            ThrottleUsage::default()
+        };
+        let user_usage = match &user_key {
+            Some(key) => Some(repository.throttle_usage(pipeline_id, key, window_since).await?),
+            None => None,
+        };
+        
+        let verdict = judge(throttle, now, &pipeline_usage, user_usage.as_ref());
+        if verdict == ThrottleVerdict::Allow {
+            if throttle.pipeline_horizon() > 0 {
+                repository.record_throttle_hit(pipeline_id, PIPELINE_SCOPE, now).await?;
+            }
+            if let Some(key) = &user_key {
+                repository.record_throttle_hit(pipeline_id, key, now).await?;
+            }
+        }
+        Ok(verdict)
+    }
+    
+    /// Run a synthetic event through a single pipeline, regardless of whether it is enabled.
+    /// The pipeline is loaded fresh from the database so unsaved cache state does not matter.
+    pub async fn test_fire(&self, pipeline_id: Uuid, event: BotEvent) -> Result<PipelineOutcome, Error> {
+        let pipeline = self.repository.get_pipeline(pipeline_id).await?
+            .ok_or_else(|| Error::NotFound(format!("Pipeline {} not found", pipeline_id)))?;
+        
+        let loaded = self.load_pipeline(&pipeline).await?;
+        
+        info!("Test-firing pipeline {} with event {}", pipeline.name, event.event_type());
+        
+        // Test fires neither count against nor wait for the pipeline's limits
+        Self::execute_pipeline(&loaded, &event, &self.context, &self.repository, false).await
+            .ok_or_else(|| Error::Internal("Failed to create execution log".to_string()))
     }
     
     /// Register a custom filter type (for plugins)
@@ -2,22 +2,30 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, debug, warn};
-use async_trait::async_trait;
 
 use crate::Error;
 use crate::eventbus::BotEvent;
 use crate::services::event_handler::{EventHandler, EventHandlerInfo, EventHandlerExt};
 use maowbot_common::models::platform::Platform;
 
+/// Handlers for one (platform, event_type), sorted by priority
+type HandlersByEvent = HashMap<(Platform, String), Vec<Arc<dyn EventHandler>>>;
+
 /// Registry for dynamic event handler registration and management.
 /// Handlers are organized by platform and event type for efficient lookup.
 pub struct EventHandlerRegistry {
     /// Map of (platform, event_type) -> handlers sorted by priority
-    handlers: Arc<RwLock<HashMap<(Platform, String), Vec<Arc<dyn EventHandler>>>>>,
+    handlers: Arc<RwLock<HandlersByEvent>>,
     /// Map of handler ID -> handler for direct lookup
     handlers_by_id: Arc<RwLock<HashMap<String, Arc<dyn EventHandler>>>>,
 }
 
+impl Default for EventHandlerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventHandlerRegistry {
     pub fn new() -> Self {
         Self {
@@ -122,7 +130,7 @@ impl EventHandlerRegistry {
         
         handlers
             .get(&key)
-            .map(|h| h.clone())
+            .cloned()
             .unwrap_or_default()
             .into_iter()
             .filter(|h| h.is_enabled())
@@ -195,6 +203,7 @@ impl EventHandlerRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use async_trait::async_trait;
     use crate::services::event_context::EventContext;
 
     struct TestHandler {
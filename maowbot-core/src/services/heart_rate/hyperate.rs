@@ -0,0 +1,78 @@
+// File: maowbot-core/src/services/heart_rate/hyperate.rs
+//
+// HypeRate speaks Phoenix channels: join `hr:<session id>`, heartbeat every
+// few seconds, and receive `hr_update` events with `{"hr": 72}`.
+
+use serde_json::{json, Value};
+
+pub const SOCKET_URL: &str = "wss://app.hyperate.io/socket/websocket";
+
+/// Phoenix closes sockets that stay silent for 60s.
+pub const HEARTBEAT_SECS: u64 = 15;
+
+#[derive(Debug, PartialEq)]
+pub enum Frame {
+    Reading(u16),
+    /// The server refused to join the session's channel
+    JoinRejected(String),
+    Other,
+}
+
+pub fn socket_url(api_key: &str) -> String {
+    format!("{SOCKET_URL}?token={}", urlencoding::encode(api_key))
+}
+
+/// The session id is the code shown in the HypeRate app (e.g. "ABC1").
+pub fn join_message(session_id: &str) -> String {
+    json!({ "topic": format!("hr:{}", session_id), "event": "phx_join", "payload": {}, "ref": 0 }).to_string()
+}
+
+pub fn heartbeat_message() -> String {
+    json!({ "topic": "phoenix", "event": "heartbeat", "payload": {}, "ref": 0 }).to_string()
+}
+
+pub fn parse_frame(text: &str) -> Frame {
+    let Ok(frame) = serde_json::from_str::<Value>(text) else {
+        return Frame::Other;
+    };
+    let topic = frame.get("topic").and_then(|v| v.as_str()).unwrap_or_default();
+    let payload = frame.get("payload");
+    match frame.get("event").and_then(|v| v.as_str()) {
+        Some("hr_update") => payload
+            .and_then(|p| p.get("hr"))
+            .and_then(|v| v.as_u64())
+            .and_then(|bpm| u16::try_from(bpm).ok())
+            .map(Frame::Reading)
+            .unwrap_or(Frame::Other),
+        Some("phx_reply") if topic.starts_with("hr:") => {
+            match payload.and_then(|p| p.get("status")).and_then(|v| v.as_str()) {
+                Some("ok") | None => Frame::Other,
+                Some(_) => Frame::JoinRejected(
+                    payload.and_then(|p| p.get("response")).map(|r| r.to_string()).unwrap_or_default(),
+                ),
+            }
+        }
+        _ => Frame::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_phoenix_frames() {
+        assert_eq!(
+            parse_frame(r#"{"event":"hr_update","payload":{"hr":83},"ref":null,"topic":"hr:internal-testing"}"#),
+            Frame::Reading(83)
+        );
+        assert_eq!(
+            parse_frame(r#"{"event":"phx_reply","payload":{"response":{},"status":"ok"},"ref":0,"topic":"phoenix"}"#),
+            Frame::Other
+        );
+        assert_eq!(
+            parse_frame(r#"{"event":"phx_reply","payload":{"response":{"reason":"unmatched topic"},"status":"error"},"ref":0,"topic":"hr:x"}"#),
+            Frame::JoinRejected(r#"{"reason":"unmatched topic"}"#.to_string())
+        );
+    }
+}
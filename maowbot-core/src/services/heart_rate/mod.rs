@@ -0,0 +1,298 @@
+// File: maowbot-core/src/services/heart_rate/mod.rs
+//
+// Live heart rate from Pulsoid or HypeRate. Every reading is forwarded to
+// VRChat as avatar parameters and pushed to plugins as a `heart_rate`
+// GameEvent for the VR overlay widget; changes in BPM are also published on
+// the bus as `BotEvent::HeartRate` for pipelines.
+
+pub mod pulsoid;
+pub mod hyperate;
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::json;
+use tokio::task::JoinHandle;
+use tokio::time::interval;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::{debug, info, trace, warn};
+
+use maowbot_osc::MaowOscManager;
+use maowbot_proto::plugs::{
+    plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse,
+};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::plugins::manager::PluginManager;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Settings that change which socket we connect to.
+const CONNECTION_KEYS: &[&str] = &[
+    "heart_rate.source",
+    "heart_rate.pulsoid.token",
+    "heart_rate.hyperate.api_key",
+    "heart_rate.hyperate.session_id",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeartRateSource {
+    Pulsoid,
+    HypeRate,
+}
+
+impl fmt::Display for HeartRateSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeartRateSource::Pulsoid => write!(f, "pulsoid"),
+            HeartRateSource::HypeRate => write!(f, "hyperate"),
+        }
+    }
+}
+
+impl FromStr for HeartRateSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pulsoid" => Ok(HeartRateSource::Pulsoid),
+            "hyperate" => Ok(HeartRateSource::HypeRate),
+            other => Err(Error::Parse(format!(
+                "Unknown heart rate source '{}' (expected pulsoid or hyperate)", other
+            ))),
+        }
+    }
+}
+
+pub struct HeartRateService {
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    osc_manager: Arc<MaowOscManager>,
+    plugin_manager: Arc<PluginManager>,
+    connection: Mutex<Option<JoinHandle<()>>>,
+    last_bpm: Mutex<Option<u16>>,
+}
+
+impl HeartRateService {
+    pub fn new(
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        osc_manager: Arc<MaowOscManager>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            event_bus,
+            settings,
+            osc_manager,
+            plugin_manager,
+            connection: Mutex::new(None),
+            last_bpm: Mutex::new(None),
+        }
+    }
+
+    /// Connects to the configured source, and reconnects whenever the source
+    /// or its credentials change.
+    pub fn start(self: &Arc<Self>) {
+        self.restart();
+        for key in CONNECTION_KEYS {
+            let weak = Arc::downgrade(self);
+            self.settings.watch(key, move |_, _| {
+                if let Some(service) = weak.upgrade() {
+                    service.restart();
+                }
+            });
+        }
+    }
+
+    /// Latest BPM, or None while disconnected.
+    pub fn current_bpm(&self) -> Option<u16> {
+        *self.last_bpm.lock()
+    }
+
+    fn restart(self: &Arc<Self>) {
+        let mut connection = self.connection.lock();
+        if let Some(handle) = connection.take() {
+            handle.abort();
+            let service = self.clone();
+            tokio::spawn(async move { service.disconnected().await });
+        }
+        let source = match self.settings.get("heart_rate.source") {
+            Some(s) if s != "off" && !s.trim().is_empty() => match s.parse::<HeartRateSource>() {
+                Ok(source) => source,
+                Err(e) => {
+                    warn!("[HeartRate] {}", e);
+                    return;
+                }
+            },
+            _ => {
+                debug!("[HeartRate] no source configured");
+                return;
+            }
+        };
+        let service = self.clone();
+        *connection = Some(tokio::spawn(async move { service.run(source).await }));
+    }
+
+    /// Keeps the source connected, backing off between attempts.
+    async fn run(self: Arc<Self>, source: HeartRateSource) {
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        let mut delay = Duration::from_secs(1);
+        loop {
+            let started = Instant::now();
+            tokio::select! {
+                res = self.connect_once(source) => match res {
+                    Ok(()) => info!("[HeartRate] {} connection closed", source),
+                    Err(e) => warn!("[HeartRate] {} connection failed: {:?}", source, e),
+                },
+                _ = shutdown_rx.changed() => return,
+            }
+            self.disconnected().await;
+            if started.elapsed() > MAX_RECONNECT_DELAY {
+                delay = Duration::from_secs(1);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown_rx.changed() => return,
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    async fn connect_once(&self, source: HeartRateSource) -> Result<(), Error> {
+        let setting = |key: &str| {
+            self.settings.get(key)
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| Error::Auth(format!("{} is not set", key)))
+        };
+        let (url, join) = match source {
+            HeartRateSource::Pulsoid => (pulsoid::socket_url(&setting("heart_rate.pulsoid.token")?), None),
+            HeartRateSource::HypeRate => (
+                hyperate::socket_url(&setting("heart_rate.hyperate.api_key")?),
+                Some(hyperate::join_message(&setting("heart_rate.hyperate.session_id")?)),
+            ),
+        };
+
+        let (ws, _) = connect_async(&url)
+            .await
+            .map_err(|e| Error::Platform(format!("{} websocket connect error: {e}", source)))?;
+        info!("[HeartRate] connected to {}", source);
+        let (mut sink, mut stream) = ws.split();
+
+        if let Some(join) = join {
+            sink.send(Message::text(join)).await
+                .map_err(|e| Error::Platform(format!("HypeRate join failed: {e}")))?;
+        }
+        // Only HypeRate expects heartbeats; Pulsoid pings at the websocket level
+        let mut heartbeat = interval(Duration::from_secs(hyperate::HEARTBEAT_SECS));
+        heartbeat.tick().await;
+
+        loop {
+            tokio::select! {
+                msg = stream.next() => {
+                    let msg = match msg {
+                        Some(Ok(m)) => m,
+                        Some(Err(e)) => return Err(Error::Platform(format!("{} websocket error: {e}", source))),
+                        None => return Ok(()),
+                    };
+                    if msg.is_close() {
+                        return Ok(());
+                    }
+                    let Message::Text(txt) = msg else { continue };
+                    let bpm = match source {
+                        HeartRateSource::Pulsoid => pulsoid::parse_message(&txt),
+                        HeartRateSource::HypeRate => match hyperate::parse_frame(&txt) {
+                            hyperate::Frame::Reading(bpm) => Some(bpm),
+                            hyperate::Frame::JoinRejected(reason) => {
+                                return Err(Error::Auth(format!("HypeRate refused the session: {}", reason)));
+                            }
+                            hyperate::Frame::Other => None,
+                        },
+                    };
+                    match bpm {
+                        Some(bpm) => self.reading(source, bpm).await,
+                        None => trace!("[HeartRate] ignoring frame: {}", txt),
+                    }
+                }
+                _ = heartbeat.tick(), if source == HeartRateSource::HypeRate => {
+                    sink.send(Message::text(hyperate::heartbeat_message())).await
+                        .map_err(|e| Error::Platform(format!("HypeRate heartbeat failed: {e}")))?;
+                }
+            }
+        }
+    }
+
+    /// Forwards one reading. A BPM of 0 means the sensor lost contact.
+    async fn reading(&self, source: HeartRateSource, bpm: u16) {
+        if bpm == 0 {
+            self.disconnected().await;
+            return;
+        }
+        let changed = self.last_bpm.lock().replace(bpm) != Some(bpm);
+
+        if self.settings.get_bool("heart_rate.osc.enabled").unwrap_or(true) {
+            self.send_osc(Some(bpm));
+        }
+        self.broadcast(json!({ "bpm": bpm, "source": source.to_string() })).await;
+
+        if changed {
+            self.event_bus.publish(BotEvent::HeartRate {
+                source: source.to_string(),
+                bpm,
+                timestamp: Utc::now(),
+            }).await;
+        }
+    }
+
+    async fn disconnected(&self) {
+        if self.last_bpm.lock().take().is_none() {
+            return;
+        }
+        if self.settings.get_bool("heart_rate.osc.enabled").unwrap_or(true) {
+            self.send_osc(None);
+        }
+        self.broadcast(json!({ "bpm": null })).await;
+    }
+
+    /// Sets the BPM (int), BPM / max_bpm (float, 0–1) and connected (bool)
+    /// parameters. A parameter whose name is blank is skipped.
+    fn send_osc(&self, bpm: Option<u16>) {
+        let param = |key: &str| self.settings.get(key).filter(|p| !p.trim().is_empty());
+        let max_bpm = self.settings.get_i64("heart_rate.osc.max_bpm").unwrap_or(200).max(1) as f32;
+
+        let mut results = Vec::new();
+        if let Some(name) = param("heart_rate.osc.connected_parameter") {
+            results.push(self.osc_manager.send_avatar_parameter_bool(&name, bpm.is_some()));
+        }
+        if let Some(bpm) = bpm {
+            if let Some(name) = param("heart_rate.osc.parameter") {
+                results.push(self.osc_manager.send_avatar_parameter_int(&name, bpm as i32));
+            }
+            if let Some(name) = param("heart_rate.osc.percent_parameter") {
+                let percent = (bpm as f32 / max_bpm).clamp(0.0, 1.0);
+                results.push(self.osc_manager.send_avatar_parameter_float(&name, percent));
+            }
+        }
+        if let Some(Err(e)) = results.into_iter().find(|r| r.is_err()) {
+            debug!("[HeartRate] OSC send failed: {:?}", e);
+        }
+    }
+
+    async fn broadcast(&self, payload: serde_json::Value) {
+        self.plugin_manager.broadcast(
+            PluginStreamResponse {
+                payload: Some(RespPayload::GameEvent(GameEvent {
+                    name: "heart_rate".to_string(),
+                    json: payload.to_string(),
+                })),
+            },
+            None,
+        ).await;
+    }
+}
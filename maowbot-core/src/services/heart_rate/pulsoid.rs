@@ -0,0 +1,22 @@
+// File: maowbot-core/src/services/heart_rate/pulsoid.rs
+//
+// Pulsoid's realtime websocket pushes one JSON frame per reading:
+// `{"measured_at": 1625310655000, "data": {"heart_rate": 72}}`.
+
+use serde_json::Value;
+
+pub const SOCKET_URL: &str = "wss://dev.pulsoid.net/api/v1/data/real_time";
+
+/// `token` needs the `data:heart_rate:read` scope.
+pub fn socket_url(token: &str) -> String {
+    format!("{SOCKET_URL}?access_token={}", urlencoding::encode(token))
+}
+
+/// The BPM in a realtime frame, if it carries one.
+pub fn parse_message(text: &str) -> Option<u16> {
+    let frame: Value = serde_json::from_str(text).ok()?;
+    frame.get("data")?
+        .get("heart_rate")?
+        .as_u64()
+        .and_then(|bpm| u16::try_from(bpm).ok())
+}
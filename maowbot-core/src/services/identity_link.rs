@@ -0,0 +1,124 @@
+// File: maowbot-core/src/services/identity_link.rs
+//
+// Self-service identity linking. A viewer types `!link` on one platform and
+// is sent a short code privately (a Twitch whisper, or a Discord DM reply).
+// Sending `!link CODE` from their account on the other platform proves they
+// own both, and the UserService folds the two user records into one.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use rand::Rng;
+use uuid::Uuid;
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::models::user::User;
+
+/// How long a code can be redeemed for.
+pub const LINK_CODE_TTL_MINUTES: i64 = 10;
+const CODE_LEN: usize = 6;
+/// No 0/O or 1/I, so codes survive being read off a screen.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Twitch's chat, Helix and EventSub identities are the same account.
+pub fn platform_family(platform: &Platform) -> &'static str {
+    match platform {
+        Platform::Twitch | Platform::TwitchIRC | Platform::TwitchEventSub => "twitch",
+        Platform::Discord => "discord",
+        Platform::VRChat => "vrchat",
+        Platform::Kick => "kick",
+        Platform::OBS => "obs",
+    }
+}
+
+/// A code waiting to be sent from another platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingLink {
+    pub user_id: Uuid,
+    pub platform: Platform,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Why a code could not be redeemed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkCodeError {
+    /// Unknown, already used or expired
+    Invalid,
+    /// Sent from the same platform it was issued on
+    SamePlatform,
+}
+
+/// What a `!link CODE` did.
+#[derive(Debug, Clone)]
+pub enum LinkOutcome {
+    /// Both identities now belong to this user
+    Linked(User),
+    /// The code was the sender's own
+    AlreadyLinked,
+    Failed(LinkCodeError),
+}
+
+/// Outstanding link codes, one per user.
+#[derive(Debug, Default)]
+pub struct LinkCodes {
+    pending: Mutex<HashMap<String, PendingLink>>,
+}
+
+impl LinkCodes {
+    /// A fresh code for `user_id`, replacing any code they already had.
+    pub fn issue(&self, user_id: Uuid, platform: Platform, now: DateTime<Utc>) -> String {
+        let mut pending = self.pending.lock();
+        pending.retain(|_, link| link.expires_at > now && link.user_id != user_id);
+        let mut rng = rand::rng();
+        let code = loop {
+            let code: String = (0..CODE_LEN)
+                .map(|_| CODE_ALPHABET[rng.random_range(0..CODE_ALPHABET.len())] as char)
+                .collect();
+            if !pending.contains_key(&code) {
+                break code;
+            }
+        };
+        pending.insert(code.clone(), PendingLink {
+            user_id,
+            platform,
+            expires_at: now + Duration::minutes(LINK_CODE_TTL_MINUTES),
+        });
+        code
+    }
+
+    /// Takes `code` if it is live and was issued on another platform than
+    /// `platform`. A code sent from the wrong platform stays usable.
+    pub fn redeem(&self, code: &str, platform: &Platform, now: DateTime<Utc>) -> Result<PendingLink, LinkCodeError> {
+        let code = code.trim().to_uppercase();
+        let mut pending = self.pending.lock();
+        pending.retain(|_, link| link.expires_at > now);
+        let link = pending.get(&code).ok_or(LinkCodeError::Invalid)?;
+        if platform_family(&link.platform) == platform_family(platform) {
+            return Err(LinkCodeError::SamePlatform);
+        }
+        pending.remove(&code).ok_or(LinkCodeError::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_only_work_once_from_another_platform_before_they_expire() {
+        let codes = LinkCodes::default();
+        let user = Uuid::new_v4();
+        let now = Utc::now();
+
+        let code = codes.issue(user, Platform::TwitchIRC, now);
+        assert_eq!(code.len(), CODE_LEN);
+        assert_eq!(codes.redeem(&code, &Platform::Twitch, now), Err(LinkCodeError::SamePlatform));
+        let link = codes.redeem(&code.to_lowercase(), &Platform::Discord, now).unwrap();
+        assert_eq!(link.user_id, user);
+        assert_eq!(codes.redeem(&code, &Platform::Discord, now), Err(LinkCodeError::Invalid));
+
+        let code = codes.issue(user, Platform::Discord, now);
+        let later = now + Duration::minutes(LINK_CODE_TTL_MINUTES + 1);
+        assert_eq!(codes.redeem(&code, &Platform::TwitchIRC, later), Err(LinkCodeError::Invalid));
+    }
+}
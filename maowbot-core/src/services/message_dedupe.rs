@@ -0,0 +1,67 @@
+// File: maowbot-core/src/services/message_dedupe.rs
+//
+// Every IRC account the bot has joined to a channel receives its own copy of
+// each chat line, so services that act on chat once per message remember the
+// ids they have handled.
+
+use std::collections::{HashSet, VecDeque};
+use parking_lot::Mutex;
+
+/// Ids remembered by default.
+pub const DEFAULT_CAPACITY: usize = 500;
+
+#[derive(Default)]
+struct Seen {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+/// The most recent message ids, forgetting the oldest past a capacity.
+pub struct MessageDedupe {
+    capacity: usize,
+    seen: Mutex<Seen>,
+}
+
+impl Default for MessageDedupe {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl MessageDedupe {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), seen: Mutex::new(Seen::default()) }
+    }
+
+    /// True the first time a message id is seen.
+    pub fn first_sighting(&self, message_id: &str) -> bool {
+        let mut seen = self.seen.lock();
+        if !seen.ids.insert(message_id.to_string()) {
+            return false;
+        }
+        seen.order.push_back(message_id.to_string());
+        while seen.order.len() > self.capacity {
+            if let Some(old) = seen.order.pop_front() {
+                seen.ids.remove(&old);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeats_are_caught_until_forgotten() {
+        let dedupe = MessageDedupe::new(2);
+        assert!(dedupe.first_sighting("a"));
+        assert!(!dedupe.first_sighting("a"));
+        assert!(dedupe.first_sighting("b"));
+        assert!(dedupe.first_sighting("c"));
+        // "a" was the oldest of three, so it has been forgotten
+        assert!(dedupe.first_sighting("a"));
+        assert!(!dedupe.first_sighting("c"));
+    }
+}
@@ -8,8 +8,8 @@ use maowbot_common::models::platform::Platform::TwitchIRC;
 use maowbot_common::models::platform::PlatformCredential;
 use maowbot_common::traits::repository_traits::CredentialsRepository;
 use crate::platforms::manager::PlatformManager;
+use crate::services::ai_safety::{chunk_for_chat, redact_pii, ShapedResponse};
 use crate::Error;
-use serde_json::Value;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use once_cell::sync::Lazy;
@@ -79,6 +79,8 @@ fn cleanup_expired_continuations() {
 /// Maximum length for Twitch chat messages
 pub const TWITCH_MAX_MSG_LENGTH: usize = 450;
 const MAX_TWITCH_MSG_LEN: usize = 450;
+/// AI answers carry their own "(1/3)" markers, so they use Twitch's full limit.
+const TWITCH_AI_MSG_LEN: usize = 500;
 
 /// Structure to store message context for commands like !sources and !continue
 #[derive(Debug, Clone)]
@@ -108,7 +110,7 @@ pub struct SourceCitation {
     pub title: String,
 }
 
-/// Global storage for message contexts
+// Global storage for message contexts
 lazy_static! {
     static ref LAST_MESSAGES: Mutex<HashMap<String, MessageContext>> = Mutex::new(HashMap::new());
 }
@@ -120,6 +122,8 @@ pub struct MessageResponse {
     pub respond_credential_id: Option<Uuid>,
     pub platform: String,
     pub channel: String,
+    /// Send the texts as replies to the message that triggered them
+    pub reply_to_message: bool,
 }
 
 /// Service for sending messages across different platforms with proper credential selection
@@ -170,20 +174,22 @@ impl MessageSender {
     /// Check whether any continuation text is pending for the channel.
     pub fn has_pending(channel: &str) -> bool {
         let map = PENDING_CONTINUATIONS.lock();
-        map.get(channel).map_or(false, |data| !data.chunks.is_empty() && !data.is_expired())
+        map.get(channel).is_some_and(|data| !data.chunks.is_empty() && !data.is_expired())
     }
 
     /// Determine which credential to use for sending messages on a given platform
     /// 
     /// Follows these rules:
     /// 1. If specified_credential_id is provided and valid, use that
-    /// 2. Find the first bot credential for the platform
-    /// 3. Find the first broadcaster credential for the platform
-    /// 4. Use the specified message_sender_user_id's credential if available
-    /// 5. Return None if no suitable credential is found
+    /// 2. The account assigned to the channel in `chat.identities`, or the platform's default there
+    /// 3. Find the first bot credential for the platform
+    /// 4. Find the first broadcaster credential for the platform
+    /// 5. Use the specified message_sender_user_id's credential if available
+    /// 6. Return None if no suitable credential is found
     pub async fn select_response_credential(
         &self,
         platform: &Platform,
+        channel: &str,
         specified_credential_id: Option<Uuid>,
         message_sender_user_id: Uuid,
     ) -> Result<Option<PlatformCredential>, Error> {
@@ -197,7 +203,15 @@ impl MessageSender {
             }
         }
 
-        // #2: Get all credentials for this platform
+        // #2: the identity assigned to this channel
+        if let Some(identities) = self.platform_manager.chat_identities() {
+            if let Some(c) = identities.credential_for(platform, channel).await? {
+                debug!("Using {}'s assigned identity: {} ({})", channel, c.user_name, c.credential_id);
+                return Ok(Some(c));
+            }
+        }
+
+        // Get all credentials for this platform
         let all_creds = self.credentials_repo.list_credentials_for_platform(platform).await?;
         
         // If no credentials exist for this platform, return None
@@ -307,9 +321,6 @@ impl MessageSender {
         segments
     }
     
-    /// Extract source citations from OpenAI API response
-
-    
     /// Handles the !continue command - sends the next segment of a truncated message
     pub async fn handle_continue_command(
         &self,
@@ -353,8 +364,9 @@ impl MessageSender {
             None => return Ok(false),
         };
 
-        // 3) send it (may await) – no lock is held here
-        self.send_twitch_message(channel, &chunk, respond_credential_id, user_id)
+        // 3) send it (may await) – no lock is held here. Queued chunks are
+        //    already sized, so they go out as-is rather than being re-split
+        self.send_twitch_chunks(channel, vec![chunk], respond_credential_id, user_id)
             .await
             .ok();
         Ok(true)
@@ -383,7 +395,7 @@ impl MessageSender {
 
             for (idx, pair) in q.iter().enumerate() {
                 // pair = ["title", "url"]
-                let title = pair.get(0).cloned().unwrap_or_default();
+                let title = pair.first().cloned().unwrap_or_default();
                 let url   = pair.get(1).cloned().unwrap_or_default();
                 let line  = format!("{}): {} — {}", idx + 1, title, url);
 
@@ -407,6 +419,22 @@ impl MessageSender {
     ) -> Result<(), Error> {
         info!("Attempting to send Twitch message to channel: {}", channel);
 
+        // ----------------------------------------------------------------
+        // 1) Split message into <=450-char chunks on word-boundaries
+        // ----------------------------------------------------------------
+        let segments = Self::split_into_chunks(message, MAX_TWITCH_MSG_LEN);
+        self.send_twitch_chunks(channel, segments, specified_credential_id, message_sender_user_id)
+            .await
+    }
+
+    /// Sends the first of `segments` now and queues the rest for `!continue`.
+    pub async fn send_twitch_chunks(
+        &self,
+        channel: &str,
+        segments: Vec<String>,
+        specified_credential_id: Option<Uuid>,
+        message_sender_user_id: Uuid,
+    ) -> Result<(), Error> {
         // Make sure the channel name starts with a # prefix for Twitch IRC
         let channel_with_hash = if !channel.starts_with('#') {
             format!("#{}", channel)
@@ -414,11 +442,6 @@ impl MessageSender {
             channel.to_string()
         };
 
-        // ----------------------------------------------------------------
-        // 1) Split message into <=450-char chunks on word-boundaries
-        // ----------------------------------------------------------------
-        let segments = Self::split_into_chunks(message, MAX_TWITCH_MSG_LEN);
-
         // Nothing to send – rare but guard anyway
         if segments.is_empty() {
             warn!("send_twitch_message called with empty text for {}", channel);
@@ -427,7 +450,7 @@ impl MessageSender {
 
         // 2) Choose credential
         let credential_opt = self
-            .select_response_credential(&TwitchIRC, specified_credential_id, message_sender_user_id)
+            .select_response_credential(&TwitchIRC, &channel_with_hash, specified_credential_id, message_sender_user_id)
             .await?;
 
         let credential = match credential_opt {
@@ -453,9 +476,9 @@ impl MessageSender {
 
         // 3) Send the FIRST chunk immediately
         info!(
-            "Sending first segment ({} / {} chars) using credential {}",
+            "Sending first segment ({} chars, {} segment(s)) using credential {}",
             segments[0].len(),
-            message.len(),
+            segments.len(),
             credential.user_name
         );
 
@@ -495,7 +518,8 @@ impl MessageSender {
         Ok(())
     }
     
-    /// Send a Twitch message with AI response including source handling
+    /// Send a Twitch message with AI response including source handling. The
+    /// text goes through the AI safety filter first; returns what was sent.
     pub async fn send_ai_response_to_twitch(
         &self,
         channel: &str,
@@ -503,7 +527,7 @@ impl MessageSender {
         raw_response: Option<&serde_json::Value>,
         respond_credential_id: Option<Uuid>,
         as_user_id: Uuid,
-    ) -> Result<(), Error> {
+    ) -> Result<ShapedResponse, Error> {
         use serde_json::Value;
         
         // Clear any existing continuations for this user when they make a new AI request
@@ -537,10 +561,29 @@ impl MessageSender {
         }
 
         // --------------------------------------------------------
-        // 2)  Delegate to the existing text‑sending helper
+        // 2)  Filter and chunk, then send the first chunk
         // --------------------------------------------------------
-        self.send_twitch_message(channel, plain_text, respond_credential_id, as_user_id)
-            .await
+        let shaped = match self.platform_manager.ai_shaper() {
+            Some(shaper) => shaper.shape("twitch-irc", channel, plain_text),
+            None => {
+                let (text, redacted) = redact_pii(plain_text);
+                ShapedResponse {
+                    chunks: chunk_for_chat(&text, TWITCH_AI_MSG_LEN, 4),
+                    tts: text,
+                    redacted,
+                    ..Default::default()
+                }
+            }
+        };
+        if shaped.redacted > 0 || shaped.masked > 0 || shaped.withheld {
+            info!(
+                "AI response for {} filtered: {} redacted, {} masked, withheld={}",
+                channel, shaped.redacted, shaped.masked, shaped.withheld
+            );
+        }
+        self.send_twitch_chunks(channel, shaped.chunks.clone(), respond_credential_id, as_user_id)
+            .await?;
+        Ok(shaped)
     }
 
     /// Send a response consisting of multiple message lines
@@ -608,33 +651,4 @@ impl MessageSender {
 
         chunks
     }
-}
-
-fn extract_sources(raw: &serde_json::Value) -> Vec<String> {
-    if let Some(arr) = raw.get("sources").and_then(|v| v.as_array()) {
-        return arr
-            .iter()
-            .filter_map(|s| {
-                Some(format!(
-                    "{} — {}",
-                    s.get("title")?.as_str()?,
-                    s.get("url")?.as_str()?
-                ))
-            })
-            .collect();
-    }
-    if let Some(arr) = raw.get("annotations").and_then(|v| v.as_array()) {
-        return arr
-            .iter()
-            .filter_map(|a| {
-                let c = a.get("url_citation")?;
-                Some(format!(
-                    "{} — {}",
-                    c.get("title")?.as_str()?,
-                    c.get("url")?.as_str()?
-                ))
-            })
-            .collect();
-    }
-    Vec::new()
 }
\ No newline at end of file
@@ -1,16 +1,17 @@
 use std::sync::Arc;
-use tokio::sync::Mutex;
 use chrono::{DateTime, Utc};
 use tracing::{debug, info, error};
 use maowbot_common::models::cache::CachedMessage;
 use maowbot_common::models::platform::Platform;
-use maowbot_common::traits::repository_traits::CredentialsRepository;
+use maowbot_common::models::user::User;
+use maowbot_common::models::workspace;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, UserAnalysisRepository};
 use crate::eventbus::{EventBus, BotEvent};
 use crate::Error;
-use crate::repositories::postgres::user_analysis::PostgresUserAnalysisRepository;
 
 use crate::auth::user_manager::{UserManager, DefaultUserManager};
 use crate::cache::message_cache::ChatCache;
+use crate::services::chat_load;
 use crate::services::user_service::UserService;
 use crate::services::{CommandService, CommandResponse};
 use crate::platforms::manager::PlatformManager;
@@ -18,7 +19,7 @@ use crate::platforms::manager::PlatformManager;
 /// The MessageService is responsible for ingesting new chat messages from any platform
 /// and for checking/processing commands (via CommandService).
 pub struct MessageService {
-    chat_cache: Arc<Mutex<ChatCache<PostgresUserAnalysisRepository>>>,
+    chat_cache: Arc<ChatCache<Arc<dyn UserAnalysisRepository>>>,
     event_bus: Arc<EventBus>,
     user_manager: Arc<DefaultUserManager>,
     pub user_service: Arc<UserService>,
@@ -29,7 +30,7 @@ pub struct MessageService {
 
 impl MessageService {
     pub fn new(
-        chat_cache: Arc<Mutex<ChatCache<PostgresUserAnalysisRepository>>>,
+        chat_cache: Arc<ChatCache<Arc<dyn UserAnalysisRepository>>>,
         event_bus: Arc<EventBus>,
         user_manager: Arc<DefaultUserManager>,
         user_service: Arc<UserService>,
@@ -49,44 +50,49 @@ impl MessageService {
         }
     }
 
-    /// Processes an incoming chat message:
-    ///  1. Converts platform string to enum.
-    ///  2. Retrieves (or creates) the user.
-    ///  3. Updates user roles if provided.
-    ///  4. Stores the message in the cache.
-    ///  5. Publishes the chat event to the EventBus.
-    ///  6. Checks for a command response from CommandService; if found, sends the lines.
-    pub async fn process_incoming_message(
+    /// Runs a slash command for a platform user and returns the reply lines.
+    /// Slash commands don't go through the chat cache or the event bus.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn process_slash_command(
         &self,
         platform: &str,
         channel: &str,
         platform_user_id: &str,
         maybe_display_name: Option<&str>,
         roles_list: &[String],
-        text: &str,
-        metadata: &[String],
-    ) -> Result<(), Error> {
-        debug!("process_incoming_message() called for platform='{}', channel='{}'", platform, channel);
+        command_name: &str,
+        options: &[(String, String)],
+    ) -> Result<Vec<String>, Error> {
+        let platform_enum = platform_enum(platform)?;
+        let user = self.platform_user(&platform_enum, platform_user_id, maybe_display_name, roles_list).await?;
+        info!(event_type = "slash_command", platform, channel, user = %user.user_id, command = command_name, "Running slash command");
 
-        // 1) Convert platform to enum
-        let platform_enum = match platform {
-            "twitch-irc" => Platform::TwitchIRC,
-            "twitch"     => Platform::Twitch,
-            "discord"    => Platform::Discord,
-            "vrchat"     => Platform::VRChat,
-            "twitch-eventsub" => Platform::TwitchEventSub,
-            other => {
-                error!("Unknown platform: {}", other);
-                return Err(Error::Platform(format!("Unknown platform: {}", other)));
-            }
-        };
+        let is_stream_online = false; // as for chat commands
+        self.command_service
+            .handle_slash_command(
+                platform,
+                channel,
+                user.user_id,
+                platform_user_id,
+                roles_list,
+                command_name,
+                options,
+                is_stream_online,
+            )
+            .await
+    }
 
-        // 2) Get or create the user
+    async fn platform_user(
+        &self,
+        platform_enum: &Platform,
+        platform_user_id: &str,
+        maybe_display_name: Option<&str>,
+        roles_list: &[String],
+    ) -> Result<User, Error> {
         let user = self.user_manager
             .get_or_create_user(platform_enum.clone(), platform_user_id, maybe_display_name)
             .await?;
 
-        // 3) Update roles if provided
         if !roles_list.is_empty() {
             if let Err(e) = self.user_service
                 .unify_platform_roles(user.user_id, platform_enum.clone(), roles_list)
@@ -95,44 +101,113 @@ impl MessageService {
                 error!("Failed to unify roles for user {:?}: {:?}", user.user_id, e);
             }
         }
+        Ok(user)
+    }
+
+    /// Whether `channel` has real chat: the cache has messages from it, or it is
+    /// the channel of an account the bot joins on connect.
+    pub async fn is_known_channel(&self, platform: &str, channel: &str) -> Result<bool, Error> {
+        if self.chat_cache.has_channel(platform, channel) {
+            return Ok(true);
+        }
+        let name = workspace::channel_key(channel);
+        let accounts = self.credentials_repo.list_credentials_for_platform(&platform_enum(platform)?).await?;
+        Ok(accounts.iter().any(|c| workspace::channel_key(&c.user_name) == name))
+    }
+
+    /// Drops a channel's cached messages, e.g. after a load test filled it.
+    pub fn forget_channel(&self, platform: &str, channel: &str) -> usize {
+        self.chat_cache.remove_channel(platform, channel)
+    }
+
+    /// Processes an incoming chat message:
+    ///  1. Converts platform string to enum.
+    ///  2. Retrieves (or creates) the user; generated load test messages get a throwaway one.
+    ///  3. Updates user roles if provided.
+    ///  4. Stores the message in the cache.
+    ///  5. Publishes the chat event to the EventBus.
+    ///  6. Stops there for messages from the bot's own accounts.
+    ///  7. Checks for a command response from CommandService; if found, sends the lines.
+    pub async fn process_incoming_message(
+        &self,
+        platform: &str,
+        channel: &str,
+        platform_user_id: &str,
+        maybe_display_name: Option<&str>,
+        roles_list: &[String],
+        text: &str,
+        metadata: &[String],
+    ) -> Result<(), Error> {
+        debug!("process_incoming_message() called for platform='{}', channel='{}'", platform, channel);
+        let started = std::time::Instant::now();
+
+        // 1) Convert platform to enum
+        let platform_enum = platform_enum(platform)?;
+
+        // 2) Get or create the user, 3) updating roles if provided
+        let user = match chat_load::generated_user(metadata, maybe_display_name) {
+            Some(user) => user,
+            None => self.platform_user(&platform_enum, platform_user_id, maybe_display_name, roles_list).await?,
+        };
 
         // 4) Add message to chat cache
         let token_count = text.split_whitespace().count();
         let cached_msg = CachedMessage {
             platform: platform.to_string(),
             channel: channel.to_string(),
+            user_id: user.user_id,
             user_name: user.global_username.clone().unwrap_or_else(|| platform_user_id.to_string()),
             text: text.to_string(),
             timestamp: Utc::now(),
             token_count,
             user_roles: roles_list.to_vec(),
         };
-        {
-            let lock = self.chat_cache.lock().await;
-            lock.add_message(cached_msg).await;
-        }
+        self.chat_cache.add_message(cached_msg);
 
         // 5) Publish chat event
         info!("💬 MESSAGE SERVICE: Publishing chat event to EventBus - platform: {}, channel: {}, user: {}, text: '{}'", 
               platform, channel, user.user_id, text);
+        let mut event_metadata = serde_json::Map::new();
+        for (key, value) in metadata.iter().filter_map(|m| m.split_once(':')) {
+            event_metadata.insert(key.to_string(), value.into());
+        }
+        event_metadata.insert("platform_user_id".into(), platform_user_id.into());
+        event_metadata.insert("username".into(), maybe_display_name.unwrap_or(platform_user_id).into());
+        event_metadata.insert("roles".into(), roles_list.to_vec().into());
         let event = BotEvent::ChatMessage {
             platform: platform.to_string(),
             channel: channel.to_string(),
             user: user.user_id.to_string(),
             text: text.to_string(),
             timestamp: Utc::now(),
-            metadata: serde_json::Map::new(),
+            metadata: event_metadata,
         };
         self.event_bus.publish(event).await;
-        info!("💬 MESSAGE SERVICE: Chat event published successfully");
+        info!(
+            event_type = "chat_message",
+            platform,
+            channel,
+            user = %user.user_id,
+            latency_ms = started.elapsed().as_millis() as u64,
+            "💬 MESSAGE SERVICE: Chat event published successfully"
+        );
+
+        // 6) Never answer the bot's own messages (a reply that triggers itself)
+        if let Some(guard) = self.platform_manager.outbound_guard() {
+            if guard.is_own_account(&platform_enum, platform_user_id, maybe_display_name).await {
+                debug!("Skipping commands for the bot's own message on {} {}", platform, channel);
+                return Ok(());
+            }
+        }
 
-        // 6) Check if it's a command
+        // 7) Check if it's a command
         let is_stream_online = false; // (placeholder or eventsub-based status if needed)
         match self.command_service
             .handle_chat_line(
                 platform,
                 channel,
                 user.user_id,
+                platform_user_id,
                 roles_list,
                 text,
                 is_stream_online,
@@ -144,7 +219,12 @@ impl MessageService {
                      respond_credential_id,
                      platform: cmd_platform,
                      channel: cmd_channel,
+                     reply_to_message,
                  }) => {
+                // The triggering message's id, when the command answers as a reply
+                let reply_to = metadata.iter()
+                    .find_map(|m| m.strip_prefix("message_id:"))
+                    .filter(|_| reply_to_message);
                 // ---------------------------------------------
                 // CHANGED: No longer calling get_ttv_secondary...
                 // Instead, CommandService decides which credential
@@ -157,10 +237,15 @@ impl MessageService {
                         let cred_opt = self.credentials_repo.get_credential_by_id(cred_id).await?;
                         if let Some(cred) = cred_opt {
                             for line in texts {
-                                if let Err(e) = self.platform_manager
-                                    .send_twitch_irc_message(&cred.user_name, &cmd_channel, &line)
-                                    .await
-                                {
+                                let sent = match reply_to {
+                                    Some(parent_id) => self.platform_manager
+                                        .send_twitch_irc_reply(&cred.user_name, &cmd_channel, parent_id, &line)
+                                        .await,
+                                    None => self.platform_manager
+                                        .send_twitch_irc_message(&cred.user_name, &cmd_channel, &line)
+                                        .await,
+                                };
+                                if let Err(e) = sent {
                                     error!("Failed to send IRC reply => {:?}", e);
                                 }
                             }
@@ -174,9 +259,13 @@ impl MessageService {
                     }
                 }
                 else if cmd_platform.eq_ignore_ascii_case("discord") {
-                    // Find a Discord bot credential to respond with
+                    // The channel's assigned identity, else the first Discord bot credential
+                    let assigned = match self.platform_manager.chat_identities() {
+                        Some(identities) => identities.credential_for(&Platform::Discord, &cmd_channel).await?,
+                        None => None,
+                    };
                     let creds = self.credentials_repo.list_credentials_for_platform(&Platform::Discord).await?;
-                    if let Some(bot_cred) = creds.iter().find(|c| c.is_bot) {
+                    if let Some(bot_cred) = assigned.as_ref().or_else(|| creds.iter().find(|c| c.is_bot)) {
                         // Extract guild ID from metadata if available
                         debug!("Discord command response metadata: {:?}", metadata);
                         let guild_id = metadata.iter()
@@ -189,16 +278,44 @@ impl MessageService {
                             .unwrap_or("");
                         
                         // Send each line as a separate message
+                        for line in texts {
+                            let sent = match reply_to {
+                                Some(message_id) => self.platform_manager
+                                    .send_discord_reply(&bot_cred.user_name, guild_id, &cmd_channel, message_id, &line)
+                                    .await,
+                                None => self.platform_manager
+                                    .send_discord_message(&bot_cred.user_name, guild_id, &cmd_channel, &line)
+                                    .await,
+                            };
+                            if let Err(e) = sent {
+                                error!("Failed to send command response via Discord => {:?}", e);
+                            }
+                        }
+                    } else {
+                        error!("No Discord bot credential found for command response");
+                    }
+                } else if cmd_platform.eq_ignore_ascii_case("kick") {
+                    // The command's chosen credential, else the Kick bot account, else the broadcaster
+                    let cred_opt = match respond_credential_id {
+                        Some(cred_id) => self.credentials_repo.get_credential_by_id(cred_id).await?,
+                        None => {
+                            let creds = self.credentials_repo.list_credentials_for_platform(&Platform::Kick).await?;
+                            creds.iter().find(|c| c.is_bot)
+                                .or_else(|| creds.iter().find(|c| c.is_broadcaster))
+                                .cloned()
+                        }
+                    };
+                    if let Some(cred) = cred_opt {
                         for line in texts {
                             if let Err(e) = self.platform_manager
-                                .send_discord_message(&bot_cred.user_name, guild_id, &cmd_channel, &line)
+                                .send_kick_message(&cred.user_name, &cmd_channel, &line)
                                 .await
                             {
-                                error!("Failed to send command response via Discord => {:?}", e);
+                                error!("Failed to send Kick reply => {:?}", e);
                             }
                         }
                     } else {
-                        error!("No Discord bot credential found for command response");
+                        error!("No Kick credential found for command response");
                     }
                 } else {
                     // If 'twitch' or 'vrchat' or something else,
@@ -221,7 +338,37 @@ impl MessageService {
         token_limit: Option<usize>,
         filter_user_name: Option<&str>,
     ) -> Vec<CachedMessage> {
-        let lock = self.chat_cache.lock().await;
-        lock.get_recent_messages(since, token_limit, filter_user_name).await
+        self.chat_cache.get_recent_messages(since, token_limit, filter_user_name)
+    }
+
+    /// Returns recent messages from one channel of the chat cache.
+    pub async fn get_channel_messages(
+        &self,
+        platform: &str,
+        channel: &str,
+        since: DateTime<Utc>,
+        token_limit: Option<usize>,
+    ) -> Vec<CachedMessage> {
+        self.chat_cache.get_channel_messages(platform, channel, since, token_limit)
+    }
+
+    /// The shared chat cache, e.g. to change a channel's retention at runtime.
+    pub fn chat_cache(&self) -> &Arc<ChatCache<Arc<dyn UserAnalysisRepository>>> {
+        &self.chat_cache
+    }
+}
+
+fn platform_enum(platform: &str) -> Result<Platform, Error> {
+    match platform {
+        "twitch-irc" => Ok(Platform::TwitchIRC),
+        "twitch"     => Ok(Platform::Twitch),
+        "discord"    => Ok(Platform::Discord),
+        "vrchat"     => Ok(Platform::VRChat),
+        "twitch-eventsub" => Ok(Platform::TwitchEventSub),
+        "kick"       => Ok(Platform::Kick),
+        other => {
+            error!("Unknown platform: {}", other);
+            Err(Error::Platform(format!("Unknown platform: {}", other)))
+        }
     }
 }
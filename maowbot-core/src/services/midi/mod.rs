@@ -0,0 +1,422 @@
+// File: maowbot-core/src/services/midi/mod.rs
+//
+// MIDI controllers as a bot control surface. Messages are read through
+// midir (ALSA on Linux, CoreMIDI on macOS, WinMM on Windows) from the first
+// input port whose name contains `midi.device`, and each note or controller
+// with a mapping in `midi_mappings` drives its action: an avatar parameter follows the key or fader, while OBS scenes and
+// chat macros fire when a key or button is pressed. In learn mode the next
+// note or controller touched is handed to whoever is waiting for it instead.
+
+pub mod parser;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
+use midir::{MidiInput, MidiInputPort};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::midi_mapping::{MidiActionKind, MidiMapping, MidiTrigger, MidiTriggerKind};
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, MidiMappingRepository};
+use maowbot_osc::MaowOscManager;
+
+use crate::eventbus::EventBus;
+use crate::platforms::manager::PlatformManager;
+use crate::services::message_sender::MessageSender;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use self::parser::{MidiMessage, MidiParser};
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// How often an open port is checked for having been unplugged.
+const PORT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+const CLIENT_NAME: &str = "maowbot";
+/// A controller counts as pressed from this value up.
+const CC_PRESSED: u8 = 64;
+const OSC_TYPES: [&str; 3] = ["bool", "int", "float"];
+
+/// A new mapping, before it is stored.
+#[derive(Debug, Clone)]
+pub struct NewMidiMapping {
+    pub name: String,
+    pub trigger: MidiTrigger,
+    pub action: MidiActionKind,
+    pub target: String,
+    pub argument: String,
+}
+
+/// Whether `value` on `trigger` counts as held down.
+pub fn is_pressed(trigger: &MidiTrigger, value: u8) -> bool {
+    match trigger.kind {
+        MidiTriggerKind::Note => value > 0,
+        MidiTriggerKind::ControlChange => value >= CC_PRESSED,
+    }
+}
+
+pub struct MidiService {
+    repo: Arc<dyn MidiMappingRepository + Send + Sync>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    osc_manager: Arc<MaowOscManager>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    mappings: RwLock<HashMap<MidiTrigger, MidiMapping>>,
+    /// Whether each trigger was last seen pressed, so controller buttons fire once per press
+    pressed: Mutex<HashMap<MidiTrigger, bool>>,
+    last_chat: Mutex<HashMap<Uuid, Instant>>,
+    learner: Mutex<Option<oneshot::Sender<MidiTrigger>>>,
+    connection: Mutex<Option<JoinHandle<()>>>,
+    connected: AtomicBool,
+}
+
+impl MidiService {
+    pub fn new(
+        repo: Arc<dyn MidiMappingRepository + Send + Sync>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        osc_manager: Arc<MaowOscManager>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            repo,
+            credentials_repo,
+            platform_manager,
+            osc_manager,
+            event_bus,
+            settings,
+            mappings: RwLock::new(HashMap::new()),
+            pressed: Mutex::new(HashMap::new()),
+            last_chat: Mutex::new(HashMap::new()),
+            learner: Mutex::new(None),
+            connection: Mutex::new(None),
+            connected: AtomicBool::new(false),
+        }
+    }
+
+    /// Loads the mappings and opens `midi.device`, reopening it whenever the
+    /// setting changes.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.reload().await {
+                warn!("[MIDI] could not load mappings: {:?}", e);
+            }
+        });
+        self.restart();
+        let weak = Arc::downgrade(self);
+        self.settings.watch("midi.device", move |_, _| {
+            if let Some(service) = weak.upgrade() {
+                service.restart();
+            }
+        });
+    }
+
+    /// The configured device, if it is open.
+    pub fn connected_device(&self) -> Option<String> {
+        if !self.connected.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.settings.get("midi.device").filter(|d| !d.trim().is_empty())
+    }
+
+    pub async fn list_mappings(&self) -> Result<Vec<MidiMapping>, Error> {
+        self.repo.list_mappings().await
+    }
+
+    async fn reload(&self) -> Result<(), Error> {
+        let mappings = self.repo.list_mappings().await?;
+        debug!("[MIDI] {} mapping(s) loaded", mappings.len());
+        *self.mappings.write() = mappings.into_iter().map(|m| (m.trigger, m)).collect();
+        Ok(())
+    }
+
+    /// Waits for the next note or controller touched on the device.
+    pub async fn learn(&self, timeout: Duration) -> Result<MidiTrigger, Error> {
+        if self.connected_device().is_none() {
+            return Err(Error::Platform("No MIDI device is open (set midi.device)".into()));
+        }
+        let (tx, rx) = oneshot::channel();
+        if self.learner.lock().replace(tx).is_some() {
+            debug!("[MIDI] a newer learn request replaced the waiting one");
+        }
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(trigger)) => Ok(trigger),
+            Ok(Err(_)) => Err(Error::Internal("Learning was cancelled by another learn request".into())),
+            Err(_) => {
+                self.learner.lock().take();
+                Err(Error::Platform(format!("Nothing was pressed within {} seconds", timeout.as_secs())))
+            }
+        }
+    }
+
+    pub async fn add_mapping(&self, new: NewMidiMapping) -> Result<MidiMapping, Error> {
+        let name = new.name.trim().to_string();
+        let target = new.target.trim().to_string();
+        if name.is_empty() || target.is_empty() {
+            return Err(Error::ValidationError("A MIDI mapping needs a name and a target".into()));
+        }
+        if !(1..=16).contains(&new.trigger.channel) || new.trigger.number > 127 {
+            return Err(Error::ValidationError("MIDI channels are 1-16 and note/controller numbers 0-127".into()));
+        }
+        let argument = new.argument.trim().to_lowercase();
+        let argument = match new.action {
+            MidiActionKind::Osc if argument.is_empty() => "bool".to_string(),
+            MidiActionKind::Osc if !OSC_TYPES.contains(&argument.as_str()) => {
+                return Err(Error::ValidationError(format!(
+                    "Unknown parameter type '{}' (expected {})", argument, OSC_TYPES.join(", ")
+                )));
+            }
+            MidiActionKind::ObsScene if argument.is_empty() => "1".to_string(),
+            MidiActionKind::ObsScene if argument.parse::<u32>().is_err() => {
+                return Err(Error::ValidationError(format!("Invalid OBS instance '{}'", argument)));
+            }
+            MidiActionKind::Chat => argument.trim_start_matches('#').to_string(),
+            _ => argument,
+        };
+        if self.repo.get_mapping_by_name(&name).await?.is_some() {
+            return Err(Error::ValidationError(format!("A MIDI mapping named '{}' already exists", name)));
+        }
+        if let Some(existing) = self.mappings.read().get(&new.trigger) {
+            return Err(Error::ValidationError(format!(
+                "{} is already mapped by '{}'", new.trigger, existing.name
+            )));
+        }
+
+        let now = Utc::now();
+        let mapping = MidiMapping {
+            mapping_id: Uuid::new_v4(),
+            name,
+            trigger: new.trigger,
+            action: new.action,
+            target,
+            argument,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+        self.repo.create_mapping(&mapping).await?;
+        info!("[MIDI] mapped {} to {} '{}'", mapping.trigger, mapping.action, mapping.target);
+        self.reload().await?;
+        Ok(mapping)
+    }
+
+    pub async fn set_enabled(&self, name: &str, enabled: bool) -> Result<MidiMapping, Error> {
+        let mut mapping = self.repo.get_mapping_by_name(name).await?
+            .ok_or_else(|| Error::NotFound(format!("No MIDI mapping named '{}'", name)))?;
+        mapping.enabled = enabled;
+        mapping.updated_at = Utc::now();
+        self.repo.update_mapping(&mapping).await?;
+        self.reload().await?;
+        Ok(mapping)
+    }
+
+    pub async fn delete_mapping(&self, name: &str) -> Result<(), Error> {
+        let mapping = self.repo.get_mapping_by_name(name).await?
+            .ok_or_else(|| Error::NotFound(format!("No MIDI mapping named '{}'", name)))?;
+        self.repo.delete_mapping(mapping.mapping_id).await?;
+        self.reload().await
+    }
+
+    fn restart(self: &Arc<Self>) {
+        let mut connection = self.connection.lock();
+        if let Some(handle) = connection.take() {
+            handle.abort();
+            self.connected.store(false, Ordering::Relaxed);
+        }
+        let Some(device) = self.settings.get("midi.device").filter(|d| !d.trim().is_empty()) else {
+            debug!("[MIDI] no device configured");
+            return;
+        };
+        let service = self.clone();
+        *connection = Some(tokio::spawn(async move { service.run(device.trim().to_string()).await }));
+    }
+
+    /// Keeps the device open, waiting longer between attempts while it's missing.
+    async fn run(self: Arc<Self>, device: String) {
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        let mut delay = Duration::from_secs(1);
+        loop {
+            let started = Instant::now();
+            tokio::select! {
+                res = self.read_device(&device) => match res {
+                    Ok(()) => info!("[MIDI] {} closed", device),
+                    Err(e) => warn!("[MIDI] {}: {:?}", device, e),
+                },
+                _ = shutdown_rx.changed() => return,
+            }
+            self.connected.store(false, Ordering::Relaxed);
+            if started.elapsed() > MAX_RECONNECT_DELAY {
+                delay = Duration::from_secs(1);
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown_rx.changed() => return,
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    async fn read_device(&self, device: &str) -> Result<(), Error> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let stop = StopOnDrop(Arc::new(AtomicBool::new(false)));
+        let watcher = {
+            let (device, stop) = (device.to_string(), stop.0.clone());
+            tokio::task::spawn_blocking(move || hold_port(&device, tx, &stop))
+        };
+
+        let mut parser = MidiParser::default();
+        while let Some(event) = rx.recv().await {
+            match event {
+                PortEvent::Opened(port) => {
+                    info!("[MIDI] reading {}", port);
+                    self.connected.store(true, Ordering::Relaxed);
+                }
+                PortEvent::Data(bytes) => {
+                    for byte in bytes {
+                        if let Some(message) = parser.push(byte) {
+                            self.handle(message).await;
+                        }
+                    }
+                }
+            }
+        }
+        watcher.await.map_err(|e| Error::Internal(format!("MIDI port task failed: {e}")))?
+    }
+
+    async fn handle(&self, message: MidiMessage) {
+        let trigger = message.trigger();
+        let value = message.value();
+        let pressed = is_pressed(&trigger, value);
+        let was_pressed = self.pressed.lock().insert(trigger, pressed).unwrap_or(false);
+
+        if pressed && !matches!(message, MidiMessage::NoteOff { .. }) {
+            if let Some(learner) = self.learner.lock().take() {
+                debug!("[MIDI] learned {}", trigger);
+                let _ = learner.send(trigger);
+                return;
+            }
+        }
+
+        let Some(mapping) = self.mappings.read().get(&trigger).filter(|m| m.enabled).cloned() else {
+            return;
+        };
+        let result = match mapping.action {
+            MidiActionKind::Osc => self.send_osc(&mapping, value, pressed),
+            // Notes fire on every key down, controllers once per press
+            _ if !pressed || (trigger.kind == MidiTriggerKind::ControlChange && was_pressed) => Ok(()),
+            MidiActionKind::ObsScene => self.switch_scene(&mapping).await,
+            MidiActionKind::Chat => self.send_chat(&mapping).await,
+        };
+        if let Err(e) = result {
+            warn!("[MIDI] '{}' failed: {:?}", mapping.name, e);
+        }
+    }
+
+    /// Bool parameters follow the key or button, int and float ones the
+    /// velocity or controller value (0–127, or 0–1 for floats).
+    fn send_osc(&self, mapping: &MidiMapping, value: u8, pressed: bool) -> Result<(), Error> {
+        let result = match mapping.argument.as_str() {
+            "int" => self.osc_manager.send_avatar_parameter_int(&mapping.target, value as i32),
+            "float" => self.osc_manager.send_avatar_parameter_float(&mapping.target, value as f32 / 127.0),
+            _ => self.osc_manager.send_avatar_parameter_bool(&mapping.target, pressed),
+        };
+        result.map_err(|e| Error::Platform(format!("OSC send failed: {e:?}")))
+    }
+
+    async fn switch_scene(&self, mapping: &MidiMapping) -> Result<(), Error> {
+        let instance = mapping.argument.parse::<u32>().unwrap_or(1);
+        let obs = self.platform_manager.get_obs_instance(instance).await?;
+        obs.get_client().set_current_scene(&mapping.target).await
+            .map_err(|e| Error::Platform(format!("OBS {} could not switch to '{}': {}", instance, mapping.target, e)))
+    }
+
+    async fn send_chat(&self, mapping: &MidiMapping) -> Result<(), Error> {
+        let cooldown = Duration::from_secs(self.settings.get_u64("midi.chat_cooldown_seconds").unwrap_or(3));
+        {
+            let mut last_chat = self.last_chat.lock();
+            let now = Instant::now();
+            if last_chat.get(&mapping.mapping_id).is_some_and(|at| now.duration_since(*at) < cooldown) {
+                debug!("[MIDI] '{}' is cooling down", mapping.name);
+                return Ok(());
+            }
+            last_chat.insert(mapping.mapping_id, now);
+        }
+        let channel = if mapping.argument.is_empty() {
+            self.credentials_repo.list_credentials_for_platform(&Platform::Twitch).await?
+                .into_iter()
+                .find(|c| c.is_broadcaster)
+                .map(|c| c.user_name)
+                .ok_or_else(|| Error::Platform("No Twitch broadcaster account to send chat macros to".into()))?
+        } else {
+            mapping.argument.clone()
+        };
+        let sender = MessageSender::new(self.credentials_repo.clone(), self.platform_manager.clone());
+        sender.send_twitch_message(&channel, &mapping.target, None, Uuid::nil()).await
+    }
+}
+
+enum PortEvent {
+    Opened(String),
+    Data(Vec<u8>),
+}
+
+/// Tells the port thread to let go once the reader is dropped, e.g. when the
+/// device setting changes or the bot shuts down.
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+fn find_port(input: &MidiInput, device: &str) -> Result<(MidiInputPort, String), Error> {
+    let wanted = device.to_lowercase();
+    let mut names = Vec::new();
+    for port in input.ports() {
+        let Ok(name) = input.port_name(&port) else { continue };
+        if name.to_lowercase().contains(&wanted) {
+            return Ok((port, name));
+        }
+        names.push(name);
+    }
+    Err(Error::Platform(if names.is_empty() {
+        "no MIDI input ports found".to_string()
+    } else {
+        format!("no MIDI input port matching '{}' (found: {})", device, names.join(", "))
+    }))
+}
+
+/// Keeps the port open on a blocking thread, since midir connections aren't
+/// `Send` on every backend. Returns once the port disappears or `stop` is set.
+fn hold_port(device: &str, tx: mpsc::UnboundedSender<PortEvent>, stop: &AtomicBool) -> Result<(), Error> {
+    let input = MidiInput::new(CLIENT_NAME)
+        .map_err(|e| Error::Platform(format!("MIDI is unavailable: {e}")))?;
+    let (port, name) = find_port(&input, device)?;
+    let _ = tx.send(PortEvent::Opened(name.clone()));
+    let data_tx = tx.clone();
+    let _connection = input
+        .connect(&port, CLIENT_NAME, move |_, bytes, _| {
+            let _ = data_tx.send(PortEvent::Data(bytes.to_vec()));
+        }, ())
+        .map_err(|e| Error::Platform(format!("could not open {name}: {e}")))?;
+
+    while !stop.load(Ordering::Relaxed) && !tx.is_closed() {
+        std::thread::sleep(PORT_CHECK_INTERVAL);
+        let still_there = MidiInput::new(CLIENT_NAME)
+            .map(|probe| probe.ports().iter().any(|p| probe.port_name(p).is_ok_and(|n| n == name)))
+            .unwrap_or(false);
+        if !still_there {
+            info!("[MIDI] {} was unplugged", name);
+            break;
+        }
+    }
+    Ok(())
+}
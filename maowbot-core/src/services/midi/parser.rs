@@ -0,0 +1,114 @@
+// File: maowbot-core/src/services/midi/parser.rs
+//
+// Turns a raw MIDI byte stream into note and controller messages. Handles
+// running status (controllers often send one status byte followed by many
+// data pairs), skips real-time bytes wherever they appear, and drops SysEx
+// and the other messages the bot has no use for.
+
+use maowbot_common::models::midi_mapping::{MidiTrigger, MidiTriggerKind};
+
+/// A channel message the bot can map. Channels are 1–16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiMessage {
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    NoteOff { channel: u8, note: u8 },
+    ControlChange { channel: u8, controller: u8, value: u8 },
+}
+
+impl MidiMessage {
+    pub fn trigger(&self) -> MidiTrigger {
+        match *self {
+            MidiMessage::NoteOn { channel, note, .. } | MidiMessage::NoteOff { channel, note } => {
+                MidiTrigger { kind: MidiTriggerKind::Note, channel, number: note }
+            }
+            MidiMessage::ControlChange { channel, controller, .. } => {
+                MidiTrigger { kind: MidiTriggerKind::ControlChange, channel, number: controller }
+            }
+        }
+    }
+
+    /// Velocity or controller value; 0 for a released note.
+    pub fn value(&self) -> u8 {
+        match *self {
+            MidiMessage::NoteOn { velocity, .. } => velocity,
+            MidiMessage::NoteOff { .. } => 0,
+            MidiMessage::ControlChange { value, .. } => value,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MidiParser {
+    status: Option<u8>,
+    data: [u8; 2],
+    len: usize,
+}
+
+impl MidiParser {
+    /// Feeds one byte; returns a message once its last data byte arrives.
+    pub fn push(&mut self, byte: u8) -> Option<MidiMessage> {
+        match byte {
+            // Real-time (clock, start/stop, active sensing) may interleave anything
+            0xF8..=0xFF => None,
+            // SysEx and system common cancel running status
+            0xF0..=0xF7 => {
+                self.status = None;
+                self.len = 0;
+                None
+            }
+            0x80..=0xEF => {
+                self.status = Some(byte);
+                self.len = 0;
+                None
+            }
+            data => {
+                let status = self.status?;
+                self.data[self.len] = data;
+                self.len += 1;
+                // Program change and channel pressure carry one data byte
+                let needed = if matches!(status & 0xF0, 0xC0 | 0xD0) { 1 } else { 2 };
+                if self.len < needed {
+                    return None;
+                }
+                self.len = 0;
+                let channel = (status & 0x0F) + 1;
+                let [first, second] = self.data;
+                match status & 0xF0 {
+                    0x90 if second > 0 => Some(MidiMessage::NoteOn { channel, note: first, velocity: second }),
+                    0x80 | 0x90 => Some(MidiMessage::NoteOff { channel, note: first }),
+                    0xB0 => Some(MidiMessage::ControlChange { channel, controller: first, value: second }),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(bytes: &[u8]) -> Vec<MidiMessage> {
+        let mut parser = MidiParser::default();
+        bytes.iter().filter_map(|&b| parser.push(b)).collect()
+    }
+
+    #[test]
+    fn test_parses_notes_and_controllers_with_running_status() {
+        let messages = parse(&[
+            0x90, 60, 100, 0xF8, 62, 90, // note on ch1, clock in between, running status
+            0x90, 60, 0,                 // note on with velocity 0 is a release
+            0xC1, 5,                     // program change, ignored
+            0xF0, 0x7E, 0x01, 0xF7,      // SysEx, ignored
+            0xBF, 7, 127, 7, 0,          // CC 7 on ch16, twice
+        ]);
+        assert_eq!(messages, vec![
+            MidiMessage::NoteOn { channel: 1, note: 60, velocity: 100 },
+            MidiMessage::NoteOn { channel: 1, note: 62, velocity: 90 },
+            MidiMessage::NoteOff { channel: 1, note: 60 },
+            MidiMessage::ControlChange { channel: 16, controller: 7, value: 127 },
+            MidiMessage::ControlChange { channel: 16, controller: 7, value: 0 },
+        ]);
+        assert_eq!(messages[4].trigger(), MidiTrigger { kind: MidiTriggerKind::ControlChange, channel: 16, number: 7 });
+    }
+}
@@ -1,13 +1,42 @@
 // File: src/services/mod.rs
 
 pub mod user_service;
+pub mod identity_link;
 
 pub mod message_service;
 pub mod message_sender;
+pub mod message_dedupe;
+pub mod outbound_guard;
+pub mod outbound_chain;
+pub mod chat_identity;
+pub mod workspace_router;
+pub mod ai_safety;
+pub mod chat_summarizer;
 // Moved all Twitch-specific things into services/twitch.
 pub mod twitch;
 pub mod discord;
 pub mod osc_toggle_service;
+pub mod drip_pack;
+pub mod vrchat_session_service;
+pub mod vrchat_presence_service;
+pub mod vrchat_group_service;
+pub mod osc_chat_relay;
+pub mod viewer_card;
+pub mod social;
+pub mod donations;
+pub mod heart_rate;
+pub mod midi;
+pub mod moderation;
+pub mod responders;
+pub mod emote_stats;
+pub mod emote_sets;
+pub mod ui_events;
+pub mod ui_settings;
+pub mod alerting;
+pub mod chat_load;
+pub mod command_spec;
+pub mod command_import;
+pub mod data_export;
 
 // New event handling system
 pub mod event_context;
@@ -0,0 +1,103 @@
+// File: maowbot-core/src/services/moderation/escalation.rs
+//
+// The escalation ladder: what the nth offense in a rule category gets. It is
+// written as a comma separated list in `moderation.escalation_ladder`, e.g.
+// "warn, timeout:600, ban"; offenses past the last step get the last step.
+
+use std::fmt;
+
+use maowbot_common::models::moderation_rule::RuleSeverity;
+use crate::Error;
+use super::MAX_TIMEOUT_SECS;
+
+pub const DEFAULT_LADDER: &str = "warn, timeout:600, ban";
+const DEFAULT_STEP_TIMEOUT_SECS: i32 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationStep {
+    /// Remove the message and warn the chatter
+    Warn,
+    Timeout(i32),
+    Ban,
+}
+
+impl EscalationStep {
+    /// What to enforce through Helix: the severity and, for timeouts, its length.
+    pub fn enforcement(self) -> (RuleSeverity, i32) {
+        match self {
+            EscalationStep::Warn => (RuleSeverity::Delete, 0),
+            EscalationStep::Timeout(secs) => (RuleSeverity::Timeout, secs),
+            EscalationStep::Ban => (RuleSeverity::Ban, 0),
+        }
+    }
+}
+
+impl fmt::Display for EscalationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EscalationStep::Warn => write!(f, "warn"),
+            EscalationStep::Timeout(secs) => write!(f, "timeout:{}", secs),
+            EscalationStep::Ban => write!(f, "ban"),
+        }
+    }
+}
+
+fn parse_step(text: &str) -> Result<EscalationStep, Error> {
+    let (name, arg) = match text.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg.trim())),
+        None => (text.trim(), None),
+    };
+    match (name.to_lowercase().as_str(), arg) {
+        ("warn" | "warning", None) => Ok(EscalationStep::Warn),
+        ("ban", None) => Ok(EscalationStep::Ban),
+        ("timeout", None) => Ok(EscalationStep::Timeout(DEFAULT_STEP_TIMEOUT_SECS)),
+        ("timeout", Some(secs)) => secs.parse::<i32>().ok()
+            .filter(|s| (1..=MAX_TIMEOUT_SECS).contains(s))
+            .map(EscalationStep::Timeout)
+            .ok_or_else(|| Error::Parse(format!(
+                "Timeout must be between 1 and {} seconds, not '{}'", MAX_TIMEOUT_SECS, secs
+            ))),
+        _ => Err(Error::Parse(format!(
+            "Unknown escalation step '{}' (expected warn, timeout:SECS or ban)", text.trim()
+        ))),
+    }
+}
+
+/// Parses a ladder such as "warn, timeout:600, ban".
+pub fn parse_ladder(text: &str) -> Result<Vec<EscalationStep>, Error> {
+    let steps = text.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(parse_step)
+        .collect::<Result<Vec<_>, _>>()?;
+    if steps.is_empty() {
+        return Err(Error::Parse("The escalation ladder needs at least one step".into()));
+    }
+    Ok(steps)
+}
+
+/// The step for the `offense`th offense, counting from 1.
+pub fn step_for(ladder: &[EscalationStep], offense: usize) -> EscalationStep {
+    ladder.get(offense.max(1) - 1)
+        .or(ladder.last())
+        .copied()
+        .unwrap_or(EscalationStep::Warn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ladder_steps_and_repeats_the_last() {
+        let ladder = parse_ladder(DEFAULT_LADDER).unwrap();
+        assert_eq!(ladder, vec![EscalationStep::Warn, EscalationStep::Timeout(600), EscalationStep::Ban]);
+        assert_eq!(step_for(&ladder, 1), EscalationStep::Warn);
+        assert_eq!(step_for(&ladder, 2), EscalationStep::Timeout(600));
+        assert_eq!(step_for(&ladder, 7), EscalationStep::Ban);
+
+        assert_eq!(parse_ladder("Timeout, timeout:3600").unwrap()[1], EscalationStep::Timeout(3600));
+        assert!(parse_ladder(" , ").is_err());
+        assert!(parse_ladder("warn, timeout:0").is_err());
+        assert!(parse_ladder("warn, kick").is_err());
+    }
+}
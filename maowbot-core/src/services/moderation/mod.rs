@@ -0,0 +1,489 @@
+// File: maowbot-core/src/services/moderation/mod.rs
+//
+// Rule-based chat moderation for the broadcaster's Twitch channel. Rules are
+// link allowlists, phrase blocklists or regexes, each with a severity
+// (delete, timeout, ban) and permits for subs, VIPs and mods. Every chat line
+// is checked against the enabled rules; the harshest match is enforced through
+// Helix and counted against its rule.
+//
+// Rules with a category escalate instead: a chatter's offenses in the category
+// are the moderation actions recorded there (across streams, within
+// `moderation.escalation_window_days`), and each new one gets the next step of
+// `moderation.escalation_ladder`. Timeouts and bans from the ladder go out as
+// `BotEvent::ModerationEscalated`, which the alert rules route to the mods;
+// a mod can pardon the chatter to start the ladder over.
+
+pub mod escalation;
+pub mod rules;
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::moderation_rule::{ModerationRule, RuleKind, RuleSeverity};
+use maowbot_common::models::user_notes::{ModerationAction, ModerationActionType};
+use maowbot_common::traits::repository_traits::{ModerationRuleRepository, UserNotesRepository};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::plugins::manager::PluginManager;
+use crate::services::message_dedupe::MessageDedupe;
+use crate::services::message_sender::MessageSender;
+use crate::services::twitch::broadcaster_channel::BroadcasterChannel;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use crate::i18n;
+use self::escalation::{parse_ladder, step_for, EscalationStep, DEFAULT_LADDER};
+use self::rules::{harshest, validate_pattern, ChatterStatus, RuleMatch, RuleSet};
+
+/// Helix allows timeouts of up to two weeks.
+const MAX_TIMEOUT_SECS: i32 = 1_209_600;
+/// Who ladder actions are recorded as.
+const AUTOMOD: &str = "automod";
+
+/// What's needed to add a rule.
+#[derive(Debug, Clone)]
+pub struct NewRule {
+    pub name: String,
+    pub kind: RuleKind,
+    pub pattern: String,
+    pub severity: RuleSeverity,
+    pub timeout_secs: i32,
+    pub permit_subs: bool,
+    pub permit_vips: bool,
+    pub permit_mods: bool,
+    /// Escalation category; empty for a fixed severity
+    pub category: String,
+}
+
+/// Changes to a rule; `None` leaves a field as it is. An empty category
+/// turns escalation off.
+#[derive(Debug, Clone, Default)]
+pub struct RuleEdit {
+    pub pattern: Option<String>,
+    pub severity: Option<RuleSeverity>,
+    pub timeout_secs: Option<i32>,
+    pub permit_subs: Option<bool>,
+    pub permit_vips: Option<bool>,
+    pub permit_mods: Option<bool>,
+    pub category: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// A chatter's standing in one escalation category.
+#[derive(Debug, Clone)]
+pub struct CategoryOffenses {
+    pub category: String,
+    /// Offenses since the last pardon, within the escalation window
+    pub count: usize,
+    pub last_offense_at: DateTime<Utc>,
+    /// What the next offense would get
+    pub next_step: EscalationStep,
+}
+
+/// The ladder step a rule match got.
+#[derive(Debug, Clone, Copy)]
+struct Escalation {
+    user_id: Uuid,
+    offense: usize,
+    step: EscalationStep,
+}
+
+pub struct ModerationService {
+    repo: Arc<dyn ModerationRuleRepository>,
+    history: Arc<dyn UserNotesRepository>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    rules: RwLock<Arc<RuleSet>>,
+    channel: BroadcasterChannel,
+    seen: MessageDedupe,
+}
+
+fn validate_timeout(secs: i32) -> Result<i32, Error> {
+    if (1..=MAX_TIMEOUT_SECS).contains(&secs) {
+        Ok(secs)
+    } else {
+        Err(Error::Parse(format!("Timeout must be between 1 and {} seconds", MAX_TIMEOUT_SECS)))
+    }
+}
+
+fn normalize_category(category: &str) -> String {
+    category.trim().to_lowercase()
+}
+
+impl ModerationService {
+    pub fn new(
+        repo: Arc<dyn ModerationRuleRepository>,
+        history: Arc<dyn UserNotesRepository>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            repo,
+            history,
+            event_bus,
+            settings,
+            plugin_manager,
+            rules: RwLock::new(Arc::new(RuleSet::default())),
+            channel: BroadcasterChannel::new(),
+            seen: MessageDedupe::default(),
+        }
+    }
+
+    /// Loads the rules, then checks every chat line of the broadcaster's channel.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.reload().await {
+                warn!("[Moderation] could not load rules: {:?}", e);
+            }
+
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Moderation] event loop stopped");
+        });
+    }
+
+    /// Recompiles the rule set from the database.
+    async fn reload(&self) -> Result<(), Error> {
+        let rules = self.repo.list_rules().await?;
+        let (set, broken) = RuleSet::compile(&rules);
+        for (name, e) in broken {
+            warn!("[Moderation] rule '{}' is skipped: {}", name, e);
+        }
+        *self.rules.write() = Arc::new(set);
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, user, text, metadata, .. } = event else { return };
+        if platform != "twitch-irc" || !self.settings.get_bool("moderation.enabled").unwrap_or(true) {
+            return;
+        }
+        let rules = self.rules.read().clone();
+        if rules.is_empty() {
+            return;
+        }
+        let channel = channel.trim_start_matches('#').to_lowercase();
+        if self.broadcaster_channel().await.as_deref() != Some(channel.as_str()) {
+            return;
+        }
+
+        let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let message_id = field("message_id");
+        if !message_id.is_empty() && !self.seen.first_sighting(&message_id) {
+            return;
+        }
+        let roles: Vec<String> = metadata.get("roles")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
+        let matches = rules.evaluate(&text, ChatterStatus::from_roles(&roles));
+        let Some(hit) = harshest(&matches) else { return };
+        let (twitch_user_id, username) = (field("platform_user_id"), field("username"));
+        info!("[Moderation] {} broke rule '{}' ({}) with \"{}\"", username, hit.name, hit.severity, hit.matched);
+
+        let escalation = match Uuid::parse_str(&user) {
+            Ok(user_id) if !hit.category.is_empty() => self.escalate(user_id, &hit.category).await,
+            _ => None,
+        };
+        let (severity, timeout_secs) = match escalation {
+            Some(e) => e.step.enforcement(),
+            None => (hit.severity, hit.timeout_secs),
+        };
+        if let Err(e) = self.enforce(&hit.name, severity, timeout_secs, &twitch_user_id, &message_id).await {
+            warn!("[Moderation] could not {} {} for rule '{}': {:?}", severity, username, hit.name, e);
+        }
+        if let Err(e) = self.repo.record_hit(hit.rule_id, Utc::now()).await {
+            warn!("[Moderation] could not count hit of '{}': {:?}", hit.name, e);
+        }
+        if let Some(escalation) = escalation {
+            self.record_escalation(escalation, hit, &channel, &username).await;
+        }
+
+        if self.settings.get_bool("moderation.warn_in_chat").unwrap_or(true) {
+            let key = match (escalation.map(|e| e.step), severity) {
+                (Some(EscalationStep::Warn), _) => "moderation.warned",
+                (_, RuleSeverity::Delete) => "moderation.removed",
+                (_, RuleSeverity::Timeout) => "moderation.timed_out",
+                (_, RuleSeverity::Ban) => "moderation.banned",
+            };
+            let sender = MessageSender::new(
+                self.plugin_manager.credentials_repo.clone(),
+                self.plugin_manager.platform_manager.clone(),
+            );
+            let text = i18n::text_for(self.plugin_manager.localizer.as_deref(), "twitch-irc", &channel, key, &[
+                ("user", &username),
+                ("seconds", &timeout_secs.to_string()),
+                ("rule", &hit.name),
+            ]);
+            if let Err(e) = sender.send_twitch_message(&channel, &text, None, Uuid::nil()).await {
+                warn!("[Moderation] could not send to #{}: {:?}", channel, e);
+            }
+        }
+    }
+
+    /// The ladder step for a new offense in `category`, or `None` (the rule's
+    /// own severity) when the history can't be read.
+    async fn escalate(&self, user_id: Uuid, category: &str) -> Option<Escalation> {
+        let offenses = match self.category_offenses(user_id).await {
+            Ok(offenses) => offenses,
+            Err(e) => {
+                warn!("[Moderation] could not read offenses of {}: {:?}", user_id, e);
+                return None;
+            }
+        };
+        let previous = offenses.iter().find(|o| o.category == category).map_or(0, |o| o.count);
+        let offense = previous + 1;
+        Some(Escalation { user_id, offense, step: step_for(&self.ladder(), offense) })
+    }
+
+    /// Records the offense in the moderation history and lets the mods know
+    /// about timeouts and bans.
+    async fn record_escalation(&self, escalation: Escalation, hit: &RuleMatch, channel: &str, username: &str) {
+        let action_type = match escalation.step {
+            EscalationStep::Warn => ModerationActionType::Warning,
+            EscalationStep::Timeout(_) => ModerationActionType::Timeout,
+            EscalationStep::Ban => ModerationActionType::Ban,
+        };
+        let mut action = ModerationAction::new(escalation.user_id, "twitch-irc", channel, action_type, AUTOMOD);
+        action.category = hit.category.clone();
+        action.reason = Some(format!("{} (offense {} in {})", hit.name, escalation.offense, hit.category));
+        if let EscalationStep::Timeout(secs) = escalation.step {
+            action.duration_seconds = Some(secs);
+        }
+        if let Err(e) = self.history.record_action(&action).await {
+            warn!("[Moderation] could not record offense of {}: {:?}", username, e);
+        }
+        info!("[Moderation] offense {} in '{}' for {} => {}", escalation.offense, hit.category, username, escalation.step);
+
+        if escalation.step != EscalationStep::Warn {
+            self.event_bus.publish(BotEvent::ModerationEscalated {
+                user_id: escalation.user_id,
+                user: username.to_string(),
+                channel: channel.to_string(),
+                category: hit.category.clone(),
+                rule: hit.name.clone(),
+                offense: escalation.offense as i64,
+                action: escalation.step.to_string(),
+                timestamp: Utc::now(),
+            }).await;
+        }
+    }
+
+    /// `moderation.escalation_ladder`, or the default when it doesn't parse.
+    fn ladder(&self) -> Vec<EscalationStep> {
+        let text = self.settings.get("moderation.escalation_ladder").unwrap_or_default();
+        if text.trim().is_empty() {
+            return parse_ladder(DEFAULT_LADDER).unwrap_or_default();
+        }
+        parse_ladder(&text).unwrap_or_else(|e| {
+            warn!("[Moderation] moderation.escalation_ladder is invalid ({}); using '{}'", e, DEFAULT_LADDER);
+            parse_ladder(DEFAULT_LADDER).unwrap_or_default()
+        })
+    }
+
+    /// The broadcaster's login, looked up again now and then while unknown.
+    async fn broadcaster_channel(&self) -> Option<String> {
+        self.channel.resolve(&*self.plugin_manager.credentials_repo).await
+    }
+
+    async fn enforce(
+        &self,
+        rule_name: &str,
+        severity: RuleSeverity,
+        timeout_secs: i32,
+        twitch_user_id: &str,
+        message_id: &str,
+    ) -> Result<(), Error> {
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await?;
+        let reason = format!("Moderation rule: {}", rule_name);
+        match severity {
+            RuleSeverity::Delete if message_id.is_empty() => {
+                Err(Error::Platform("Message has no id to delete".into()))
+            }
+            RuleSeverity::Delete => {
+                helix.client.delete_chat_message(&helix.broadcaster_id, &helix.broadcaster_id, message_id).await
+            }
+            RuleSeverity::Timeout | RuleSeverity::Ban => {
+                let duration = (severity == RuleSeverity::Timeout).then_some(timeout_secs.max(1) as u32);
+                helix.client.ban_user(&helix.broadcaster_id, &helix.broadcaster_id, twitch_user_id, duration, Some(&reason)).await
+            }
+        }
+    }
+
+    pub async fn add_rule(&self, new: NewRule) -> Result<ModerationRule, Error> {
+        let name = new.name.trim().to_string();
+        if name.is_empty() {
+            return Err(Error::Parse("A rule needs a name".into()));
+        }
+        if self.repo.get_rule_by_name(&name).await?.is_some() {
+            return Err(Error::Parse(format!("A rule named '{}' already exists", name)));
+        }
+        validate_pattern(new.kind, &new.pattern)?;
+        let now = Utc::now();
+        let rule = ModerationRule {
+            rule_id: Uuid::new_v4(),
+            name,
+            kind: new.kind,
+            pattern: new.pattern.trim().to_string(),
+            severity: new.severity,
+            timeout_secs: validate_timeout(new.timeout_secs)?,
+            permit_subs: new.permit_subs,
+            permit_vips: new.permit_vips,
+            permit_mods: new.permit_mods,
+            category: normalize_category(&new.category),
+            enabled: true,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.repo.create_rule(&rule).await?;
+        self.reload().await?;
+        info!("[Moderation] added {} rule '{}'", rule.kind, rule.name);
+        Ok(rule)
+    }
+
+    pub async fn edit_rule(&self, name: &str, edit: RuleEdit) -> Result<ModerationRule, Error> {
+        let mut rule = self.get_rule(name).await?;
+        if let Some(pattern) = edit.pattern {
+            validate_pattern(rule.kind, &pattern)?;
+            rule.pattern = pattern.trim().to_string();
+        }
+        if let Some(severity) = edit.severity {
+            rule.severity = severity;
+        }
+        if let Some(secs) = edit.timeout_secs {
+            rule.timeout_secs = validate_timeout(secs)?;
+        }
+        rule.permit_subs = edit.permit_subs.unwrap_or(rule.permit_subs);
+        rule.permit_vips = edit.permit_vips.unwrap_or(rule.permit_vips);
+        rule.permit_mods = edit.permit_mods.unwrap_or(rule.permit_mods);
+        if let Some(category) = edit.category {
+            rule.category = normalize_category(&category);
+        }
+        rule.enabled = edit.enabled.unwrap_or(rule.enabled);
+        rule.updated_at = Utc::now();
+        self.repo.update_rule(&rule).await?;
+        self.reload().await?;
+        Ok(rule)
+    }
+
+    pub async fn delete_rule(&self, name: &str) -> Result<(), Error> {
+        let rule = self.get_rule(name).await?;
+        self.repo.delete_rule(rule.rule_id).await?;
+        self.reload().await?;
+        info!("[Moderation] deleted rule '{}'", rule.name);
+        Ok(())
+    }
+
+    pub async fn reset_counter(&self, name: &str) -> Result<ModerationRule, Error> {
+        let rule = self.get_rule(name).await?;
+        self.repo.reset_hits(rule.rule_id).await?;
+        self.get_rule(name).await
+    }
+
+    pub async fn get_rule(&self, name: &str) -> Result<ModerationRule, Error> {
+        self.repo.get_rule_by_name(name.trim()).await?
+            .ok_or_else(|| Error::NotFound(format!("No rule named '{}'", name.trim())))
+    }
+
+    pub async fn list_rules(&self) -> Result<Vec<ModerationRule>, Error> {
+        self.repo.list_rules().await
+    }
+
+    /// A user by UUID or global username.
+    async fn resolve_user(&self, user: &str) -> Result<(Uuid, Option<String>), Error> {
+        let found = match Uuid::parse_str(user.trim()) {
+            Ok(id) => self.plugin_manager.user_repo.get(id).await?,
+            Err(_) => self.plugin_manager.user_repo.get_by_global_username(user.trim()).await?,
+        };
+        found.map(|u| (u.user_id, u.global_username))
+            .ok_or_else(|| Error::NotFound(format!("User '{}' not found", user.trim())))
+    }
+
+    /// Offenses per category since the last pardon, within the escalation window.
+    async fn category_offenses(&self, user_id: Uuid) -> Result<Vec<CategoryOffenses>, Error> {
+        let days = self.settings.get_u64("moderation.escalation_window_days").unwrap_or(90);
+        let since = (days > 0).then(|| Utc::now() - Duration::days(days as i64));
+        let ladder = self.ladder();
+
+        let mut by_category: BTreeMap<String, (usize, DateTime<Utc>)> = BTreeMap::new();
+        for action in self.history.list_offenses(user_id, since).await? {
+            let entry = by_category.entry(action.category).or_insert((0, action.created_at));
+            entry.0 += 1;
+            entry.1 = entry.1.max(action.created_at);
+        }
+        Ok(by_category.into_iter()
+            .map(|(category, (count, last_offense_at))| CategoryOffenses {
+                category,
+                count,
+                last_offense_at,
+                next_step: step_for(&ladder, count + 1),
+            })
+            .collect())
+    }
+
+    /// Where `user` stands on the escalation ladder, per category.
+    pub async fn offenses(&self, user: &str) -> Result<Vec<CategoryOffenses>, Error> {
+        let (user_id, _) = self.resolve_user(user).await?;
+        self.category_offenses(user_id).await
+    }
+
+    /// Starts the ladder over for `user` in `category`, or in every category
+    /// with offenses when `None`. With `lift`, an active Twitch ban or timeout
+    /// is lifted too. Returns the pardoned categories.
+    pub async fn pardon(&self, user: &str, category: Option<&str>, lift: bool, moderator: &str) -> Result<Vec<String>, Error> {
+        let (user_id, username) = self.resolve_user(user).await?;
+        let categories: Vec<String> = match category.map(normalize_category).filter(|c| !c.is_empty()) {
+            Some(category) => vec![category],
+            None => self.category_offenses(user_id).await?.into_iter().map(|o| o.category).collect(),
+        };
+        let channel = self.broadcaster_channel().await.unwrap_or_default();
+        for category in &categories {
+            let mut action = ModerationAction::new(user_id, "twitch-irc", &channel, ModerationActionType::Pardon, moderator);
+            action.category = category.clone();
+            self.history.record_action(&action).await?;
+        }
+
+        if lift {
+            let login = username.ok_or_else(|| Error::NotFound("The user has no username to look up on Twitch".into()))?;
+            let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await?;
+            let twitch_id = helix.client.fetch_user_id(&login).await?
+                .ok_or_else(|| Error::NotFound(format!("No Twitch user '{}'", login)))?;
+            helix.client.unban_user(&helix.broadcaster_id, &helix.broadcaster_id, &twitch_id).await?;
+            let action = ModerationAction::new(user_id, "twitch-irc", &channel, ModerationActionType::Unban, moderator);
+            self.history.record_action(&action).await?;
+        }
+        info!("[Moderation] {} pardoned {} in {:?}{}", moderator, user.trim(), categories,
+            if lift { " and lifted the ban" } else { "" });
+        Ok(categories)
+    }
+
+    /// Every enabled rule `text` would break for a chatter with `roles`,
+    /// harshest first, without acting on it.
+    pub fn test(&self, text: &str, roles: &[String]) -> Vec<RuleMatch> {
+        let mut matches = self.rules.read().evaluate(text, ChatterStatus::from_roles(roles));
+        matches.sort_by_key(|m| std::cmp::Reverse((m.severity, m.timeout_secs)));
+        matches
+    }
+}
@@ -0,0 +1,252 @@
+// File: maowbot-core/src/services/moderation/rules.rs
+//
+// Compiles moderation rules into matchers and evaluates chat lines against them.
+
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+use uuid::Uuid;
+
+use maowbot_common::models::moderation_rule::{ModerationRule, RuleKind, RuleSeverity};
+use crate::Error;
+
+/// Upper bound on a compiled rule, so one pathological regex can't eat memory.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Domain-looking tokens. Without a scheme only common TLDs count, so
+/// "lol.ok" isn't taken for a link.
+static LINK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:(https?)://)?((?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\.)+([a-z]{2,24}))\b")
+        .expect("link regex")
+});
+
+const BARE_LINK_TLDS: &[&str] = &[
+    "com", "net", "org", "io", "tv", "gg", "ly", "co", "me", "xyz", "ru", "de", "uk", "info",
+    "biz", "link", "app", "dev", "live", "shop", "site", "online", "club", "top", "to", "us",
+    "be", "cc", "fm", "ws", "su", "tk", "ml", "ga", "cf", "gq", "pw", "click", "store", "fun",
+];
+
+/// Which roles a chatter holds, for rule permits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChatterStatus {
+    pub is_sub: bool,
+    pub is_vip: bool,
+    pub is_mod: bool,
+    pub is_broadcaster: bool,
+}
+
+impl ChatterStatus {
+    /// From IRC badge roles such as "subscriber", "vip", "mod", "broadcaster".
+    pub fn from_roles<S: AsRef<str>>(roles: &[S]) -> Self {
+        let has = |names: &[&str]| roles.iter().any(|r| names.iter().any(|n| r.as_ref().eq_ignore_ascii_case(n)));
+        Self {
+            is_sub: has(&["subscriber", "founder", "sub"]),
+            is_vip: has(&["vip"]),
+            is_mod: has(&["mod", "moderator"]),
+            is_broadcaster: has(&["broadcaster"]),
+        }
+    }
+}
+
+/// A rule that matched a message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    pub rule_id: Uuid,
+    pub name: String,
+    pub severity: RuleSeverity,
+    pub timeout_secs: i32,
+    /// The rule's escalation category; empty when it doesn't escalate
+    pub category: String,
+    /// The offending part of the message
+    pub matched: String,
+}
+
+enum Matcher {
+    /// Allowlisted domains; every other link matches
+    Links(Vec<String>),
+    Pattern(Regex),
+}
+
+struct CompiledRule {
+    rule: ModerationRule,
+    matcher: Matcher,
+}
+
+/// Links in `text`, as lowercase domains.
+pub fn find_links(text: &str) -> Vec<String> {
+    LINK_RE.captures_iter(text)
+        .filter(|c| c.get(1).is_some()
+            || c.get(3).is_some_and(|tld| BARE_LINK_TLDS.contains(&tld.as_str().to_lowercase().as_str())))
+        .filter_map(|c| c.get(2).map(|d| d.as_str().to_lowercase()))
+        .collect()
+}
+
+fn domain_allowed(domain: &str, allowed: &[String]) -> bool {
+    allowed.iter().any(|a| domain == a || domain.ends_with(&format!(".{}", a)))
+}
+
+fn compile(kind: RuleKind, pattern: &str) -> Result<Matcher, Error> {
+    match kind {
+        RuleKind::Link => Ok(Matcher::Links(
+            pattern.split(|c: char| c == ',' || c.is_whitespace())
+                .map(|d| d.trim().trim_start_matches("www.").to_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect(),
+        )),
+        RuleKind::Phrase => {
+            let alternatives: Vec<String> = pattern.split('|')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(|p| {
+                    let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+                    let start = if word(p.chars().next()) { r"\b" } else { "" };
+                    let end = if word(p.chars().last()) { r"\b" } else { "" };
+                    format!("{}{}{}", start, regex::escape(p), end)
+                })
+                .collect();
+            if alternatives.is_empty() {
+                return Err(Error::Parse("A phrase rule needs at least one phrase".into()));
+            }
+            build_regex(&alternatives.join("|")).map(Matcher::Pattern)
+        }
+        RuleKind::Regex => build_regex(pattern).map(Matcher::Pattern),
+    }
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, Error> {
+    if pattern.trim().is_empty() {
+        return Err(Error::Parse("Empty pattern".into()));
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| Error::Parse(format!("Invalid pattern: {}", e)))
+}
+
+/// Checks that a rule's pattern compiles.
+pub fn validate_pattern(kind: RuleKind, pattern: &str) -> Result<(), Error> {
+    compile(kind, pattern).map(|_| ())
+}
+
+/// Every enabled rule, compiled.
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Compiles the enabled rules; ones whose pattern no longer compiles are
+    /// returned by name so they can be reported.
+    pub fn compile(rules: &[ModerationRule]) -> (Self, Vec<(String, Error)>) {
+        let mut compiled = Vec::new();
+        let mut broken = Vec::new();
+        for rule in rules.iter().filter(|r| r.enabled) {
+            match compile(rule.kind, &rule.pattern) {
+                Ok(matcher) => compiled.push(CompiledRule { rule: rule.clone(), matcher }),
+                Err(e) => broken.push((rule.name.clone(), e)),
+            }
+        }
+        (Self { rules: compiled }, broken)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Every rule the message breaks, skipping rules the chatter is permitted
+    /// past. The broadcaster is never matched.
+    pub fn evaluate(&self, text: &str, chatter: ChatterStatus) -> Vec<RuleMatch> {
+        if chatter.is_broadcaster {
+            return Vec::new();
+        }
+        let links = if self.rules.iter().any(|r| matches!(r.matcher, Matcher::Links(_))) {
+            find_links(text)
+        } else {
+            Vec::new()
+        };
+
+        self.rules.iter()
+            .filter(|r| !((r.rule.permit_subs && chatter.is_sub)
+                || (r.rule.permit_vips && chatter.is_vip)
+                || (r.rule.permit_mods && chatter.is_mod)))
+            .filter_map(|r| {
+                let matched = match &r.matcher {
+                    Matcher::Links(allowed) => links.iter().find(|d| !domain_allowed(d, allowed)).cloned(),
+                    Matcher::Pattern(re) => re.find(text).map(|m| m.as_str().to_string()),
+                }?;
+                Some(RuleMatch {
+                    rule_id: r.rule.rule_id,
+                    name: r.rule.name.clone(),
+                    severity: r.rule.severity,
+                    timeout_secs: r.rule.timeout_secs,
+                    category: r.rule.category.clone(),
+                    matched,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The match to act on: the harshest severity, then the longest timeout.
+pub fn harshest(matches: &[RuleMatch]) -> Option<&RuleMatch> {
+    matches.iter().max_by_key(|m| (m.severity, m.timeout_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule(name: &str, kind: RuleKind, pattern: &str, severity: RuleSeverity) -> ModerationRule {
+        ModerationRule {
+            rule_id: Uuid::new_v4(),
+            name: name.to_string(),
+            kind,
+            pattern: pattern.to_string(),
+            severity,
+            timeout_secs: 600,
+            permit_subs: false,
+            permit_vips: false,
+            permit_mods: true,
+            category: String::new(),
+            enabled: true,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_finds_links_but_not_sentence_dots() {
+        assert_eq!(find_links("check https://Example.org/x and discord.gg/abc"), vec!["example.org", "discord.gg"]);
+        assert!(find_links("well...ok then. end.of.story").is_empty());
+    }
+
+    #[test]
+    fn test_evaluates_rules_with_permits() {
+        let mut links = rule("links", RuleKind::Link, "youtube.com, clips.twitch.tv", RuleSeverity::Delete);
+        links.permit_subs = true;
+        let (set, broken) = RuleSet::compile(&[
+            links,
+            rule("spam", RuleKind::Phrase, "buy followers|cheap viewers", RuleSeverity::Ban),
+            rule("caps", RuleKind::Regex, "(?-i)[A-Z ]{20,}", RuleSeverity::Timeout),
+            rule("bad", RuleKind::Regex, "(unclosed", RuleSeverity::Delete),
+        ]);
+        assert_eq!(broken.len(), 1);
+
+        let viewer = ChatterStatus::default();
+        assert!(set.evaluate("see www.youtube.com/watch and m.youtube.com", viewer).is_empty());
+        assert_eq!(set.evaluate("go to evil.xyz now", viewer)[0].matched, "evil.xyz");
+        assert!(set.evaluate("go to evil.xyz now", ChatterStatus { is_sub: true, ..viewer }).is_empty());
+
+        // Phrases match whole words only
+        assert!(set.evaluate("rebuy followers", viewer).is_empty());
+        let matches = set.evaluate("Buy Followers at spam.com", viewer);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(harshest(&matches).unwrap().name, "spam");
+
+        assert!(set.evaluate("Buy followers", ChatterStatus { is_mod: true, ..viewer }).is_empty());
+        assert!(set.evaluate("Buy followers", ChatterStatus { is_broadcaster: true, ..viewer }).is_empty());
+    }
+}
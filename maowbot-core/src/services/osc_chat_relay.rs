@@ -0,0 +1,349 @@
+// File: maowbot-core/src/services/osc_chat_relay.rs
+//
+// Twitch chat in the VRChat chatbox, for streamers in VR without an overlay.
+// With `osc.relay.enabled` on, chat lines that pass `osc.relay.filters`
+// (mentions of the streamer, messages from mods, highlighted messages) are
+// queued and shown one at a time as "name: text". Each stays up at least
+// `osc.relay.min_interval_seconds`, longer ones get time to be read, and when
+// chat outpaces the chatbox the oldest queued lines are dropped so what's
+// shown is never more than a little behind.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+use maowbot_osc::vrchat::chatbox::{send_chatbox_message, ChatboxMessage};
+use maowbot_osc::MaowOscManager;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::services::message_dedupe::MessageDedupe;
+use crate::settings::SettingsRegistry;
+
+/// The most VRChat shows in the chatbox.
+pub const CHATBOX_MAX_CHARS: usize = 144;
+/// Characters a viewer reads per second, for holding long lines longer.
+const READ_CHARS_PER_SEC: f64 = 25.0;
+/// Queued lines older than this are dropped instead of shown.
+const STALE_AFTER: StdDuration = StdDuration::from_secs(30);
+/// Message ids remembered so a line seen by several IRC logins is relayed once.
+const SEEN_MESSAGE_IDS: usize = 256;
+
+/// Which chat lines go to the chatbox.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelayFilters {
+    /// Lines naming the streamer, with or without the @
+    pub mentions: bool,
+    /// Lines from the channel's moderators
+    pub mods: bool,
+    /// "Highlight my message" channel point redemptions
+    pub highlighted: bool,
+}
+
+impl RelayFilters {
+    pub const NAMES: [&'static str; 3] = ["mentions", "mods", "highlighted"];
+
+    /// Parses a comma-separated list of filter names; `all` turns on every filter.
+    pub fn parse(list: &str) -> Result<RelayFilters, String> {
+        let mut filters = RelayFilters::default();
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            match name.to_ascii_lowercase().as_str() {
+                "mentions" => filters.mentions = true,
+                "mods" => filters.mods = true,
+                "highlighted" => filters.highlighted = true,
+                "all" => filters = RelayFilters { mentions: true, mods: true, highlighted: true },
+                other => return Err(format!(
+                    "Unknown relay filter '{}' (expected {} or all)", other, Self::NAMES.join(", ")
+                )),
+            }
+        }
+        Ok(filters)
+    }
+
+    /// The first filter `line` passes, if any.
+    pub fn matches(&self, line: &RelayCandidate<'_>) -> Option<&'static str> {
+        if self.highlighted && line.msg_id == "highlighted-message" {
+            return Some("highlighted");
+        }
+        // The streamer's own lines aren't news to them
+        let is_broadcaster = line.roles.iter().any(|r| r == "broadcaster");
+        if self.mods && !is_broadcaster && line.roles.iter().any(|r| r == "mod" || r == "moderator") {
+            return Some("mods");
+        }
+        if self.mentions && !is_broadcaster && mentions(line.text, line.channel.trim_start_matches('#')) {
+            return Some("mentions");
+        }
+        None
+    }
+}
+
+/// The parts of a chat line the filters look at.
+#[derive(Debug, Clone, Copy)]
+pub struct RelayCandidate<'a> {
+    pub channel: &'a str,
+    pub text: &'a str,
+    pub roles: &'a [String],
+    /// Twitch's `msg-id` tag, e.g. "highlighted-message"
+    pub msg_id: &'a str,
+}
+
+/// Whether `text` names `login` as a whole word, with or without the @.
+fn mentions(text: &str, login: &str) -> bool {
+    !login.is_empty()
+        && text
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .any(|word| word.eq_ignore_ascii_case(login))
+}
+
+/// Cuts `s` to `max` characters, ending in "…" when anything was cut.
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// "name: text", with the name cut to `max_name` characters and the whole
+/// line to what the chatbox shows.
+pub fn chatbox_line(name: &str, text: &str, max_name: usize) -> String {
+    let name = truncate(name.trim(), max_name.max(1));
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate(&format!("{}: {}", name, text), CHATBOX_MAX_CHARS)
+}
+
+/// How long a line stays in the chatbox before the next one replaces it.
+pub fn hold_time(line: &str, min_interval: StdDuration) -> StdDuration {
+    let reading = StdDuration::from_secs_f64(line.chars().count() as f64 / READ_CHARS_PER_SEC);
+    min_interval.max(reading)
+}
+
+struct Pending {
+    line: String,
+    queued_at: Instant,
+}
+
+#[derive(Default)]
+struct RelayState {
+    queue: VecDeque<Pending>,
+}
+
+pub struct OscChatRelayService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    osc_manager: Option<Arc<MaowOscManager>>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    state: Mutex<RelayState>,
+    seen: MessageDedupe,
+    queued: Notify,
+}
+
+impl OscChatRelayService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        osc_manager: Option<Arc<MaowOscManager>>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            credentials_repo,
+            osc_manager,
+            event_bus,
+            settings,
+            state: Mutex::new(RelayState::default()),
+            seen: MessageDedupe::new(SEEN_MESSAGE_IDS),
+            queued: Notify::new(),
+        }
+    }
+
+    /// Watches chat for lines to relay and feeds the chatbox from the queue.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[OscRelay] event loop stopped");
+        });
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                let hold = match service.next_line() {
+                    Some(line) => {
+                        service.show(&line);
+                        hold_time(&line, service.min_interval())
+                    }
+                    None => {
+                        tokio::select! {
+                            _ = service.queued.notified() => continue,
+                            Ok(_) = shutdown_rx.changed() => {
+                                if *shutdown_rx.borrow() {
+                                    break;
+                                }
+                                continue;
+                            }
+                        }
+                    }
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(hold) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[OscRelay] chatbox loop stopped");
+        });
+    }
+
+    fn min_interval(&self) -> StdDuration {
+        StdDuration::from_secs(self.settings.get_u64("osc.relay.min_interval_seconds").unwrap_or(3))
+    }
+
+    fn filters(&self) -> RelayFilters {
+        let list = self.settings.get("osc.relay.filters")
+            .unwrap_or_else(|| RelayFilters::NAMES.join(","));
+        RelayFilters::parse(&list).unwrap_or_else(|e| {
+            warn!("Ignoring osc.relay.filters: {}", e);
+            RelayFilters { mentions: true, mods: true, highlighted: true }
+        })
+    }
+
+    /// Whether `channel` is on `osc.relay.channels` (blank relays every channel).
+    fn relays_channel(&self, channel: &str) -> bool {
+        let list = self.settings.get("osc.relay.channels").unwrap_or_default();
+        let channel = channel.trim_start_matches('#');
+        let mut entries = list.split(',').map(|c| c.trim().trim_start_matches('#')).filter(|c| !c.is_empty()).peekable();
+        entries.peek().is_none() || entries.any(|c| c.eq_ignore_ascii_case(channel))
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, user, text, metadata, .. } = event else { return };
+        if platform != "twitch-irc" || !self.settings.get_bool("osc.relay.enabled").unwrap_or(false) {
+            return;
+        }
+        if !self.relays_channel(&channel) {
+            return;
+        }
+        let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let roles: Vec<String> = metadata.get("roles")
+            .and_then(|v| v.as_array())
+            .map(|roles| roles.iter().filter_map(|r| r.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        let msg_id = field("msg_id");
+        let candidate = RelayCandidate { channel: &channel, text: &text, roles: &roles, msg_id: &msg_id };
+        let Some(reason) = self.filters().matches(&candidate) else { return };
+
+        let message_id = field("message_id");
+        if !message_id.is_empty() && !self.seen.first_sighting(&message_id) {
+            return;
+        }
+        // Don't echo the bot's own replies back into the chatbox
+        let sender_id = field("platform_user_id");
+        if self.is_own_account(&sender_id).await {
+            return;
+        }
+
+        let name = match field("username") {
+            name if name.is_empty() => user,
+            name => name,
+        };
+        let max_name = self.settings.get_u64("osc.relay.max_name_length").unwrap_or(12) as usize;
+        let line = chatbox_line(&name, &text, max_name);
+        debug!("[OscRelay] queueing {} line from {}", reason, name);
+        self.enqueue(line);
+    }
+
+    async fn is_own_account(&self, platform_user_id: &str) -> bool {
+        if platform_user_id.is_empty() {
+            return false;
+        }
+        match self.credentials_repo.list_credentials_for_platform(&Platform::TwitchIRC).await {
+            Ok(creds) => creds.iter().any(|c| c.platform_id.as_deref() == Some(platform_user_id)),
+            Err(e) => {
+                debug!("[OscRelay] could not list Twitch IRC credentials: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// Queues a line, dropping the oldest ones past `osc.relay.max_queue`.
+    fn enqueue(&self, line: String) {
+        let max_queue = self.settings.get_u64("osc.relay.max_queue").unwrap_or(5).max(1) as usize;
+        {
+            let mut state = self.state.lock();
+            state.queue.push_back(Pending { line, queued_at: Instant::now() });
+            while state.queue.len() > max_queue {
+                if let Some(dropped) = state.queue.pop_front() {
+                    debug!("[OscRelay] queue full, dropped '{}'", dropped.line);
+                }
+            }
+        }
+        self.queued.notify_one();
+    }
+
+    /// The oldest queued line that isn't stale yet.
+    fn next_line(&self) -> Option<String> {
+        let mut state = self.state.lock();
+        while let Some(pending) = state.queue.pop_front() {
+            if pending.queued_at.elapsed() <= STALE_AFTER {
+                return Some(pending.line);
+            }
+            debug!("[OscRelay] dropped stale '{}'", pending.line);
+        }
+        None
+    }
+
+    fn show(&self, line: &str) {
+        let Some(osc) = self.osc_manager.as_ref() else { return };
+        let mut msg = ChatboxMessage::new(line, true);
+        msg.play_notification_sound = false;
+        if let Err(e) = send_chatbox_message(osc, &msg) {
+            debug!("[OscRelay] chatbox send failed: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relay_filters_and_chatbox_lines() {
+        let filters = RelayFilters::parse("mentions, highlighted").unwrap();
+        let viewer = vec!["subscriber".to_string()];
+        let moderator = vec!["moderator".to_string(), "mod".to_string()];
+        let line = |text, roles, msg_id| RelayCandidate { channel: "#kittyn", text, roles, msg_id };
+
+        assert_eq!(filters.matches(&line("hi @Kittyn!", &viewer, "")), Some("mentions"));
+        assert_eq!(filters.matches(&line("kittynation is great", &viewer, "")), None);
+        assert_eq!(filters.matches(&line("look at me", &viewer, "highlighted-message")), Some("highlighted"));
+        assert_eq!(filters.matches(&line("a mod speaks", &moderator, "")), None);
+        assert_eq!(RelayFilters::parse("all").unwrap().matches(&line("a mod speaks", &moderator, "")), Some("mods"));
+        assert!(RelayFilters::parse("mentions,everything").is_err());
+
+        assert_eq!(chatbox_line("AVeryLongDisplayName", "hello\nthere", 8), "AVeryLo…: hello there");
+        assert_eq!(chatbox_line("x", &"a".repeat(300), 12).chars().count(), CHATBOX_MAX_CHARS);
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use chrono::{Duration, Utc};
 use tokio::sync::RwLock;
 use tokio::time;
@@ -6,7 +8,7 @@ use tracing::{info, warn, error};
 use uuid::Uuid;
 use maowbot_common::{
     error::Error,
-    models::osc_toggle::{OscTrigger, OscToggleState, OscParameterValue},
+    models::osc_toggle::{OscTrigger, OscToggleState, OscParameterValue, OscChatControl},
     traits::osc_toggle_traits::OscToggleRepository,
 };
 use maowbot_osc::MaowOscManager;
@@ -14,6 +16,27 @@ use maowbot_osc::MaowOscManager;
 pub struct OscToggleService {
     osc_manager: Arc<RwLock<Option<MaowOscManager>>>,
     toggle_repo: Arc<dyn OscToggleRepository>,
+    chat_state: Arc<parking_lot::Mutex<ChatControlState>>,
+}
+
+/// What `!toggle` and `!set` remember between uses. Kept in memory, so a
+/// restart forgets cooldowns and assumes every bool is off.
+#[derive(Default)]
+struct ChatControlState {
+    /// Last bool sent per control, which `!toggle` flips
+    bools: HashMap<String, bool>,
+    last_used: HashMap<String, Instant>,
+}
+
+/// Why a `!toggle` or `!set` from chat wasn't carried out.
+#[derive(Debug)]
+pub enum OscChatRefusal {
+    Unknown,
+    NotAllowed { role: String },
+    Cooldown { seconds: u64 },
+    NotToggle,
+    BadValue(String),
+    Failed(Error),
 }
 
 
@@ -25,6 +48,7 @@ impl OscToggleService {
         Self {
             osc_manager,
             toggle_repo,
+            chat_state: Arc::new(parking_lot::Mutex::new(ChatControlState::default())),
         }
     }
     
@@ -52,7 +76,7 @@ impl OscToggleService {
         
         // Parse the on value
         let on_value = OscParameterValue::from_string(&trigger.parameter_type, &trigger.on_value)
-            .map_err(|e| Error::ValidationError(e))?;
+            .map_err(Error::ValidationError)?;
         
         // Send OSC message to turn on the toggle
         self.send_osc_parameter(&trigger.parameter_name, on_value).await?;
@@ -95,7 +119,7 @@ impl OscToggleService {
     pub async fn deactivate_toggle(&self, state_id: i32, trigger: &OscTrigger) -> Result<(), Error> {
         // Parse the off value
         let off_value = OscParameterValue::from_string(&trigger.parameter_type, &trigger.off_value)
-            .map_err(|e| Error::ValidationError(e))?;
+            .map_err(Error::ValidationError)?;
         
         // Send OSC message to turn off the toggle
         self.send_osc_parameter(&trigger.parameter_name, off_value).await?;
@@ -121,6 +145,73 @@ impl OscToggleService {
         Ok(())
     }
     
+    /// `!toggle <name>`: flips a bool control and returns what it sent.
+    pub async fn chat_toggle(&self, name: &str, roles: &[String]) -> Result<OscParameterValue, OscChatRefusal> {
+        let control = self.chat_control(name, roles).await?;
+        if control.parameter_type != "bool" {
+            return Err(OscChatRefusal::NotToggle);
+        }
+        let on = !self.chat_state.lock().bools.get(&control.name).copied().unwrap_or(false);
+        self.send_chat_value(&control, OscParameterValue::Bool(on)).await
+    }
+
+    /// `!set <name> <value>`: sends a value clamped into the control's range
+    /// and returns what it sent.
+    pub async fn chat_set(&self, name: &str, raw_value: &str, roles: &[String]) -> Result<OscParameterValue, OscChatRefusal> {
+        let control = self.chat_control(name, roles).await?;
+        let value = control.value_from_chat(raw_value).map_err(OscChatRefusal::BadValue)?;
+        self.send_chat_value(&control, value).await
+    }
+
+    /// Looks up an enabled control and checks the caller may use it right now.
+    async fn chat_control(&self, name: &str, roles: &[String]) -> Result<OscChatControl, OscChatRefusal> {
+        let control = self.toggle_repo.get_chat_control(name).await
+            .map_err(OscChatRefusal::Failed)?
+            .filter(|c| c.enabled)
+            .ok_or(OscChatRefusal::Unknown)?;
+        if !control.allows(roles) {
+            return Err(OscChatRefusal::NotAllowed { role: control.min_role.clone() });
+        }
+        if control.cooldown_seconds > 0 {
+            let cooldown = std::time::Duration::from_secs(control.cooldown_seconds as u64);
+            if let Some(last) = self.chat_state.lock().last_used.get(&control.name) {
+                let elapsed = last.elapsed();
+                if elapsed < cooldown {
+                    return Err(OscChatRefusal::Cooldown { seconds: (cooldown - elapsed).as_secs().max(1) });
+                }
+            }
+        }
+        Ok(control)
+    }
+
+    async fn send_chat_value(&self, control: &OscChatControl, value: OscParameterValue) -> Result<OscParameterValue, OscChatRefusal> {
+        self.send_osc_parameter(&control.parameter_name, value.clone()).await
+            .map_err(OscChatRefusal::Failed)?;
+
+        let mut state = self.chat_state.lock();
+        state.last_used.insert(control.name.clone(), Instant::now());
+        if let OscParameterValue::Bool(on) = value {
+            state.bools.insert(control.name.clone(), on);
+        }
+        info!("Chat set OSC parameter {} to {}", control.parameter_name, value);
+        Ok(value)
+    }
+
+    /// Turns a bool avatar parameter on and back off after `seconds`, e.g. to
+    /// set off a confetti effect.
+    pub async fn pulse_parameter(&self, parameter_name: &str, seconds: u64) -> Result<(), Error> {
+        self.send_osc_parameter(parameter_name, OscParameterValue::Bool(true)).await?;
+        let service = self.clone();
+        let parameter_name = parameter_name.to_string();
+        tokio::spawn(async move {
+            time::sleep(time::Duration::from_secs(seconds.max(1))).await;
+            if let Err(e) = service.send_osc_parameter(&parameter_name, OscParameterValue::Bool(false)).await {
+                warn!("Failed to turn OSC parameter {} back off: {}", parameter_name, e);
+            }
+        });
+        Ok(())
+    }
+
     async fn send_osc_parameter(&self, parameter_name: &str, value: OscParameterValue) -> Result<(), Error> {
         let osc_guard = self.osc_manager.read().await;
         if let Some(osc_manager) = osc_guard.as_ref() {
@@ -150,6 +241,7 @@ impl Clone for OscToggleService {
         Self {
             osc_manager: Arc::clone(&self.osc_manager),
             toggle_repo: Arc::clone(&self.toggle_repo),
+            chat_state: Arc::clone(&self.chat_state),
         }
     }
 }
\ No newline at end of file
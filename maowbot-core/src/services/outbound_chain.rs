@@ -0,0 +1,369 @@
+// File: maowbot-core/src/services/outbound_chain.rs
+//
+// Rewrites outgoing chat before it is sent. The PlatformManager runs every
+// outbound chat line through the chain configured for its platform
+// (`outbound.middleware.<platform>`, falling back to `outbound.middleware`),
+// then asks the outbound guard about each resulting line. A middleware may
+// change a message, split it into several, or drop it by returning nothing.
+//
+// Built-in middleware, in the order they are usually listed:
+//   templates     - expands {platform} {channel} {account} {date} {time} and {var.NAME}
+//                   (the value of the `outbound.var.NAME` config key)
+//   profanity     - masks the words listed in `outbound.profanity_words`
+//   shorten_links - shortens long URLs through `outbound.shortener_url`
+//   split         - splits messages over the platform's length limit
+//   log           - logs every line that leaves the chain
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use regex::Regex;
+use tracing::{debug, info, warn};
+
+use crate::settings::SettingsRegistry;
+
+/// Used when neither `outbound.middleware.<platform>` nor `outbound.middleware` is set.
+pub const DEFAULT_CHAIN: &str = "profanity,shorten_links,split";
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{([A-Za-z0-9_.-]+)\}").unwrap());
+static URL: Lazy<Regex> = Lazy::new(|| Regex::new(r"https?://[^\s<>]+").unwrap());
+
+/// Where a message is going.
+#[derive(Debug, Clone, Copy)]
+pub struct OutboundTarget<'a> {
+    pub platform: &'a str,
+    pub account: &'a str,
+    pub channel: &'a str,
+}
+
+/// One step of the outbound chain.
+#[async_trait]
+pub trait OutboundMiddleware: Send + Sync {
+    /// Name used in the `outbound.middleware` settings.
+    fn name(&self) -> &'static str;
+
+    /// The lines to send in place of `text`; empty drops the message.
+    async fn apply(&self, target: &OutboundTarget<'_>, text: String) -> Vec<String>;
+}
+
+pub struct OutboundChain {
+    settings: Arc<SettingsRegistry>,
+    middleware: HashMap<&'static str, Arc<dyn OutboundMiddleware>>,
+}
+
+impl OutboundChain {
+    /// A chain with the built-in middleware available.
+    pub fn new(settings: Arc<SettingsRegistry>) -> Self {
+        let mut chain = Self { settings: settings.clone(), middleware: HashMap::new() };
+        chain.register(Arc::new(TemplateExpander { settings: settings.clone() }));
+        chain.register(Arc::new(ProfanityFilter { settings: settings.clone(), compiled: Mutex::new(None) }));
+        chain.register(Arc::new(LinkShortener::new(settings)));
+        chain.register(Arc::new(LengthSplitter));
+        chain.register(Arc::new(OutboundLogger));
+        chain
+    }
+
+    /// Makes `middleware` usable by name in the settings (replacing one of the same name).
+    pub fn register(&mut self, middleware: Arc<dyn OutboundMiddleware>) {
+        self.middleware.insert(middleware.name(), middleware);
+    }
+
+    /// The configured middleware names for `platform`, in order.
+    pub fn configured(&self, platform: &str) -> Vec<String> {
+        let raw = self.settings.get(&format!("outbound.middleware.{}", platform.to_lowercase()))
+            .filter(|v| !v.trim().is_empty())
+            .or_else(|| self.settings.get("outbound.middleware"))
+            .unwrap_or_else(|| DEFAULT_CHAIN.to_string());
+        parse_chain(&raw)
+    }
+
+    /// Runs `text` through the platform's chain and returns the lines to send.
+    pub async fn process(&self, target: &OutboundTarget<'_>, text: &str) -> Vec<String> {
+        let mut lines = vec![text.to_string()];
+        for name in self.configured(target.platform) {
+            let Some(middleware) = self.middleware.get(name.as_str()) else {
+                debug!("[OutboundChain] unknown middleware '{}' for {}", name, target.platform);
+                continue;
+            };
+            let mut next = Vec::with_capacity(lines.len());
+            for line in lines {
+                next.extend(middleware.apply(target, line).await);
+            }
+            lines = next;
+            if lines.is_empty() {
+                debug!("[OutboundChain] '{}' dropped a message to {} {}", name, target.platform, target.channel);
+                break;
+            }
+        }
+        lines.retain(|l| !l.trim().is_empty());
+        lines
+    }
+}
+
+/// "templates, Split ,,log" => ["templates", "split", "log"]; "none" or blank => [].
+fn parse_chain(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty() && s != "none")
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Built-in middleware
+// ---------------------------------------------------------------------------
+
+struct TemplateExpander {
+    settings: Arc<SettingsRegistry>,
+}
+
+#[async_trait]
+impl OutboundMiddleware for TemplateExpander {
+    fn name(&self) -> &'static str {
+        "templates"
+    }
+
+    async fn apply(&self, target: &OutboundTarget<'_>, text: String) -> Vec<String> {
+        let now = Utc::now();
+        let expanded = PLACEHOLDER.replace_all(&text, |caps: &regex::Captures| {
+            let key = &caps[1];
+            let value = match key {
+                "platform" => Some(target.platform.to_string()),
+                "channel" => Some(target.channel.to_string()),
+                "account" => Some(target.account.to_string()),
+                "date" => Some(now.format("%Y-%m-%d").to_string()),
+                "time" => Some(now.format("%H:%M").to_string()),
+                _ => key.strip_prefix("var.")
+                    .and_then(|name| self.settings.get(&format!("outbound.var.{}", name))),
+            };
+            // Unknown placeholders are left as written
+            value.unwrap_or_else(|| caps[0].to_string())
+        });
+        vec![expanded.into_owned()]
+    }
+}
+
+struct ProfanityFilter {
+    settings: Arc<SettingsRegistry>,
+    /// The word list the regex was built from, and the regex
+    compiled: Mutex<Option<(String, Regex)>>,
+}
+
+impl ProfanityFilter {
+    fn pattern(&self) -> Option<Regex> {
+        let words = self.settings.get("outbound.profanity_words").unwrap_or_default();
+        let mut compiled = self.compiled.lock();
+        if let Some((source, re)) = compiled.as_ref() {
+            if *source == words {
+                return Some(re.clone());
+            }
+        }
+        let re = profanity_regex(&words)?;
+        *compiled = Some((words, re.clone()));
+        Some(re)
+    }
+}
+
+/// A case-insensitive whole-word regex for a comma-separated word list.
+pub(crate) fn profanity_regex(words: &str) -> Option<Regex> {
+    let alternatives: Vec<String> = words.split(',')
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .map(regex::escape)
+        .collect();
+    if alternatives.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|"))).ok()
+}
+
+fn mask(re: &Regex, text: &str) -> String {
+    re.replace_all(text, |caps: &regex::Captures| "*".repeat(caps[0].chars().count()))
+        .into_owned()
+}
+
+#[async_trait]
+impl OutboundMiddleware for ProfanityFilter {
+    fn name(&self) -> &'static str {
+        "profanity"
+    }
+
+    async fn apply(&self, _target: &OutboundTarget<'_>, text: String) -> Vec<String> {
+        match self.pattern() {
+            Some(re) => vec![mask(&re, &text)],
+            None => vec![text],
+        }
+    }
+}
+
+struct LinkShortener {
+    settings: Arc<SettingsRegistry>,
+    http: reqwest::Client,
+    /// long URL => short URL
+    cache: Mutex<HashMap<String, String>>,
+}
+
+/// Shortened links remembered before the cache is cleared.
+const SHORT_LINK_CACHE_SIZE: usize = 500;
+
+impl LinkShortener {
+    fn new(settings: Arc<SettingsRegistry>) -> Self {
+        Self {
+            settings,
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .unwrap_or_default(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn shorten(&self, endpoint: &str, url: &str) -> Option<String> {
+        if let Some(short) = self.cache.lock().get(url) {
+            return Some(short.clone());
+        }
+        let request_url = endpoint.replace("{url}", &urlencoding::encode(url));
+        let body = match self.http.get(&request_url).send().await {
+            Ok(resp) if resp.status().is_success() => resp.text().await.ok()?,
+            Ok(resp) => {
+                warn!("[OutboundChain] link shortener returned {}", resp.status());
+                return None;
+            }
+            Err(e) => {
+                warn!("[OutboundChain] link shortener failed: {}", e);
+                return None;
+            }
+        };
+        let short = body.trim().to_string();
+        if !short.starts_with("http") || short.len() >= url.len() {
+            return None;
+        }
+        let mut cache = self.cache.lock();
+        if cache.len() >= SHORT_LINK_CACHE_SIZE {
+            cache.clear();
+        }
+        cache.insert(url.to_string(), short.clone());
+        Some(short)
+    }
+}
+
+#[async_trait]
+impl OutboundMiddleware for LinkShortener {
+    fn name(&self) -> &'static str {
+        "shorten_links"
+    }
+
+    async fn apply(&self, _target: &OutboundTarget<'_>, text: String) -> Vec<String> {
+        let Some(endpoint) = self.settings.get("outbound.shortener_url").filter(|e| e.contains("{url}")) else {
+            return vec![text];
+        };
+        let min_length = self.settings.get_u64("outbound.shorten_min_length").unwrap_or(60) as usize;
+        let long_urls: Vec<String> = URL.find_iter(&text)
+            .map(|m| m.as_str().to_string())
+            .filter(|u| u.len() >= min_length)
+            .collect();
+
+        let mut out = text;
+        for url in long_urls {
+            if let Some(short) = self.shorten(&endpoint, &url).await {
+                out = out.replace(&url, &short);
+            }
+        }
+        vec![out]
+    }
+}
+
+/// Hard message length limits, in characters.
+pub fn platform_max_length(platform: &str) -> Option<usize> {
+    match platform.to_lowercase().as_str() {
+        "twitch-irc" | "twitch" | "kick" => Some(500),
+        "discord" => Some(2000),
+        _ => None,
+    }
+}
+
+/// Splits `text` into pieces of at most `max` characters, breaking at spaces
+/// where possible.
+pub(crate) fn split_text(text: &str, max: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = text.trim();
+    while rest.chars().count() > max {
+        // Byte offset of the first character past the limit
+        let cut = rest.char_indices().nth(max).map(|(i, _)| i).unwrap_or(rest.len());
+        let at = if rest[cut..].starts_with(char::is_whitespace) {
+            cut
+        } else {
+            rest[..cut].rfind(char::is_whitespace).filter(|&i| i > 0).unwrap_or(cut)
+        };
+        parts.push(rest[..at].trim_end().to_string());
+        rest = rest[at..].trim_start();
+    }
+    if !rest.is_empty() {
+        parts.push(rest.to_string());
+    }
+    parts
+}
+
+struct LengthSplitter;
+
+#[async_trait]
+impl OutboundMiddleware for LengthSplitter {
+    fn name(&self) -> &'static str {
+        "split"
+    }
+
+    async fn apply(&self, target: &OutboundTarget<'_>, text: String) -> Vec<String> {
+        match platform_max_length(target.platform) {
+            Some(max) if text.chars().count() > max => split_text(&text, max),
+            _ => vec![text],
+        }
+    }
+}
+
+struct OutboundLogger;
+
+#[async_trait]
+impl OutboundMiddleware for OutboundLogger {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn apply(&self, target: &OutboundTarget<'_>, text: String) -> Vec<String> {
+        info!(
+            target: "outbound",
+            "{} {} as {}: {}", target.platform, target.channel, target.account, text
+        );
+        vec![text]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits_at_spaces_within_the_limit() {
+        let parts = split_text("aaaa bbbb cccc", 9);
+        assert_eq!(parts, vec!["aaaa bbbb", "cccc"]);
+        // No space to break at: cut at the limit, counting characters not bytes
+        let parts = split_text("ééééé", 2);
+        assert_eq!(parts, vec!["éé", "éé", "é"]);
+        assert_eq!(split_text("short", 500), vec!["short"]);
+    }
+
+    #[test]
+    fn test_masks_whole_words_only() {
+        let re = profanity_regex(" heck, Darn ,").unwrap();
+        assert_eq!(mask(&re, "Heck, darn it! checkers"), "****, **** it! checkers");
+        assert!(profanity_regex(" , ").is_none());
+    }
+
+    #[test]
+    fn test_chain_names_are_normalised() {
+        assert_eq!(parse_chain("templates, Split ,,log"), vec!["templates", "split", "log"]);
+        assert!(parse_chain("none").is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+// File: maowbot-core/src/services/outbound_guard.rs
+//
+// Keeps the bot from flooding chat. The PlatformManager asks `allow` before
+// every outbound chat line: the exact same text to the same channel within
+// `outbound.dedupe_seconds` is dropped, as is anything past
+// `outbound.max_messages` per channel in `outbound.window_seconds`. The
+// message service asks `is_own_account` so messages from the bot's own
+// accounts never trigger commands (a reply that triggers itself forever).
+// The counters show up in `diagnostics metrics`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use parking_lot::Mutex;
+use tracing::{debug, info};
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::settings::SettingsRegistry;
+
+/// How long the list of the bot's own accounts is trusted before re-reading it.
+const OWN_ACCOUNTS_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboundVerdict {
+    Send,
+    /// Sent the same text to this channel too recently
+    Duplicate,
+    /// The channel's outbound rate is used up
+    RateLimited,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutboundLimits {
+    /// Zero turns deduplication off
+    pub dedupe: Duration,
+    /// Zero means no limit
+    pub max_messages: usize,
+    pub window: Duration,
+}
+
+impl OutboundLimits {
+    pub fn from_settings(settings: &SettingsRegistry) -> Self {
+        Self {
+            dedupe: Duration::from_secs(settings.get_u64("outbound.dedupe_seconds").unwrap_or(30)),
+            max_messages: settings.get_u64("outbound.max_messages").unwrap_or(20) as usize,
+            window: Duration::from_secs(settings.get_u64("outbound.window_seconds").unwrap_or(30).max(1)),
+        }
+    }
+}
+
+/// Totals since startup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutboundCounters {
+    pub sent: u64,
+    pub duplicates_dropped: u64,
+    pub rate_limited: u64,
+    pub own_messages_ignored: u64,
+}
+
+/// What the bot recently sent to one channel.
+#[derive(Debug, Default)]
+struct ChannelHistory {
+    sent: VecDeque<(Instant, String)>,
+}
+
+impl ChannelHistory {
+    /// Judges `text` and, if it may be sent, records it.
+    fn check(&mut self, text: &str, now: Instant, limits: &OutboundLimits) -> OutboundVerdict {
+        let keep = limits.dedupe.max(limits.window);
+        while self.sent.front().is_some_and(|(at, _)| now.duration_since(*at) >= keep) {
+            self.sent.pop_front();
+        }
+        let text = text.trim();
+        let repeated = self.sent.iter()
+            .any(|(at, sent)| now.duration_since(*at) < limits.dedupe && sent == text);
+        if repeated {
+            return OutboundVerdict::Duplicate;
+        }
+        if limits.max_messages > 0 {
+            let in_window = self.sent.iter()
+                .filter(|(at, _)| now.duration_since(*at) < limits.window)
+                .count();
+            if in_window >= limits.max_messages {
+                return OutboundVerdict::RateLimited;
+            }
+        }
+        self.sent.push_back((now, text.to_string()));
+        OutboundVerdict::Send
+    }
+}
+
+pub struct OutboundGuard {
+    settings: Arc<SettingsRegistry>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    /// "platform:channel" => what was sent there
+    channels: Mutex<HashMap<String, ChannelHistory>>,
+    /// platform => (when read, the bot accounts' ids and names)
+    own_accounts: Mutex<HashMap<String, (Instant, Vec<String>)>>,
+    sent: AtomicU64,
+    duplicates_dropped: AtomicU64,
+    rate_limited: AtomicU64,
+    own_messages_ignored: AtomicU64,
+}
+
+impl OutboundGuard {
+    pub fn new(
+        settings: Arc<SettingsRegistry>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            settings,
+            credentials_repo,
+            channels: Mutex::new(HashMap::new()),
+            own_accounts: Mutex::new(HashMap::new()),
+            sent: AtomicU64::new(0),
+            duplicates_dropped: AtomicU64::new(0),
+            rate_limited: AtomicU64::new(0),
+            own_messages_ignored: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether `text` may go to `channel` now. Allowed messages count against
+    /// the channel's rate.
+    pub fn allow(&self, platform: &str, channel: &str, text: &str) -> bool {
+        let limits = OutboundLimits::from_settings(&self.settings);
+        let key = format!("{}:{}", platform, channel.to_lowercase());
+        let verdict = self.channels.lock()
+            .entry(key)
+            .or_default()
+            .check(text, Instant::now(), &limits);
+        match verdict {
+            OutboundVerdict::Send => {
+                self.sent.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            OutboundVerdict::Duplicate => {
+                self.duplicates_dropped.fetch_add(1, Ordering::Relaxed);
+                debug!("[OutboundGuard] dropped repeated message to {} {}: '{}'", platform, channel, text);
+                false
+            }
+            OutboundVerdict::RateLimited => {
+                self.rate_limited.fetch_add(1, Ordering::Relaxed);
+                info!("[OutboundGuard] {} {} is over its outbound rate; dropped '{}'", platform, channel, text);
+                false
+            }
+        }
+    }
+
+    /// Whether a chat message came from one of the bot's own accounts and
+    /// should not be answered. Always false when `outbound.ignore_own_messages`
+    /// is off.
+    pub async fn is_own_account(&self, platform: &Platform, platform_user_id: &str, name: Option<&str>) -> bool {
+        if !self.settings.get_bool("outbound.ignore_own_messages").unwrap_or(true) {
+            return false;
+        }
+        let key = platform.to_string();
+        let cached = self.own_accounts.lock()
+            .get(&key)
+            .filter(|(read_at, _)| read_at.elapsed() < OWN_ACCOUNTS_TTL)
+            .map(|(_, accounts)| accounts.clone());
+        let accounts = match cached {
+            Some(accounts) => accounts,
+            None => {
+                let accounts: Vec<String> = match self.credentials_repo.list_credentials_for_platform(platform).await {
+                    Ok(creds) => creds.into_iter()
+                        .filter(|c| c.is_bot)
+                        .flat_map(|c| c.platform_id.into_iter().chain(std::iter::once(c.user_name.to_lowercase())))
+                        .collect(),
+                    Err(e) => {
+                        debug!("[OutboundGuard] couldn't list {} credentials: {:?}", key, e);
+                        return false;
+                    }
+                };
+                self.own_accounts.lock().insert(key, (Instant::now(), accounts.clone()));
+                accounts
+            }
+        };
+        let own = accounts.iter().any(|a| {
+            a == platform_user_id || name.is_some_and(|n| a.eq_ignore_ascii_case(n))
+        });
+        if own {
+            self.own_messages_ignored.fetch_add(1, Ordering::Relaxed);
+        }
+        own
+    }
+
+    pub fn counters(&self) -> OutboundCounters {
+        OutboundCounters {
+            sent: self.sent.load(Ordering::Relaxed),
+            duplicates_dropped: self.duplicates_dropped.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            own_messages_ignored: self.own_messages_ignored.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(dedupe: u64, max_messages: usize, window: u64) -> OutboundLimits {
+        OutboundLimits {
+            dedupe: Duration::from_secs(dedupe),
+            max_messages,
+            window: Duration::from_secs(window),
+        }
+    }
+
+    #[test]
+    fn test_drops_repeats_inside_the_dedupe_window() {
+        let limits = limits(30, 0, 30);
+        let mut history = ChannelHistory::default();
+        let start = Instant::now();
+        assert_eq!(history.check("hi", start, &limits), OutboundVerdict::Send);
+        assert_eq!(history.check("hi ", start + Duration::from_secs(10), &limits), OutboundVerdict::Duplicate);
+        assert_eq!(history.check("hello", start + Duration::from_secs(10), &limits), OutboundVerdict::Send);
+        assert_eq!(history.check("hi", start + Duration::from_secs(31), &limits), OutboundVerdict::Send);
+    }
+
+    #[test]
+    fn test_limits_messages_per_window() {
+        let limits = limits(0, 2, 30);
+        let mut history = ChannelHistory::default();
+        let start = Instant::now();
+        assert_eq!(history.check("a", start, &limits), OutboundVerdict::Send);
+        assert_eq!(history.check("a", start + Duration::from_secs(1), &limits), OutboundVerdict::Send);
+        assert_eq!(history.check("b", start + Duration::from_secs(2), &limits), OutboundVerdict::RateLimited);
+        assert_eq!(history.check("b", start + Duration::from_secs(30), &limits), OutboundVerdict::Send);
+    }
+}
@@ -0,0 +1,184 @@
+// File: maowbot-core/src/services/responders/matcher.rs
+//
+// Compiles responder patterns and finds the responders a chat line triggers.
+
+use regex::{Regex, RegexBuilder};
+
+use maowbot_common::models::responder::{Responder, ResponderMatch};
+use crate::Error;
+
+/// Upper bound on a compiled pattern, so one pathological regex can't eat memory.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+enum Matcher {
+    /// A regex, or the keywords joined into one alternation
+    Pattern(Regex),
+    /// One regex per keyword; all must match
+    AllOf(Vec<Regex>),
+}
+
+struct CompiledResponder {
+    responder: Responder,
+    matcher: Matcher,
+}
+
+/// `keyword` as a case-insensitive whole-word pattern. Keywords starting or
+/// ending in punctuation (":)", "o7!") aren't bounded on that side.
+fn keyword_pattern(keyword: &str) -> String {
+    let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if word(keyword.chars().next()) { r"\b" } else { "" };
+    let end = if word(keyword.chars().last()) { r"\b" } else { "" };
+    format!("{}{}{}", start, regex::escape(keyword), end)
+}
+
+fn keywords(pattern: &str) -> Result<Vec<String>, Error> {
+    let keywords: Vec<String> = pattern.split('|')
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(keyword_pattern)
+        .collect();
+    if keywords.is_empty() {
+        return Err(Error::Parse("A keyword responder needs at least one keyword".into()));
+    }
+    Ok(keywords)
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, Error> {
+    if pattern.trim().is_empty() {
+        return Err(Error::Parse("Empty pattern".into()));
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| Error::Parse(format!("Invalid pattern: {}", e)))
+}
+
+fn compile(kind: ResponderMatch, pattern: &str) -> Result<Matcher, Error> {
+    match kind {
+        ResponderMatch::Regex => build_regex(pattern).map(Matcher::Pattern),
+        ResponderMatch::AnyKeyword => build_regex(&keywords(pattern)?.join("|")).map(Matcher::Pattern),
+        ResponderMatch::AllKeywords => keywords(pattern)?
+            .iter()
+            .map(String::as_str)
+            .map(build_regex)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Matcher::AllOf),
+    }
+}
+
+/// Checks that a responder's pattern compiles.
+pub fn validate_pattern(kind: ResponderMatch, pattern: &str) -> Result<(), Error> {
+    compile(kind, pattern).map(|_| ())
+}
+
+/// Whether a responder listens on `platform` and `channel`. Channels are
+/// compared without a leading '#', case-insensitively.
+pub fn listens_on(responder: &Responder, platform: &str, channel: &str) -> bool {
+    let normalize = |c: &str| c.trim_start_matches('#').to_lowercase();
+    responder.platform.as_deref().is_none_or(|p| p.eq_ignore_ascii_case(platform))
+        && responder.channel.as_deref().is_none_or(|c| normalize(c) == normalize(channel))
+}
+
+/// Every enabled responder, compiled.
+#[derive(Default)]
+pub struct ResponderSet {
+    responders: Vec<CompiledResponder>,
+}
+
+impl ResponderSet {
+    /// Compiles the enabled responders; ones whose pattern no longer compiles
+    /// are returned by name so they can be reported.
+    pub fn compile(responders: &[Responder]) -> (Self, Vec<(String, Error)>) {
+        let mut compiled = Vec::new();
+        let mut broken = Vec::new();
+        for responder in responders.iter().filter(|r| r.enabled) {
+            match compile(responder.match_kind, &responder.pattern) {
+                Ok(matcher) => compiled.push(CompiledResponder { responder: responder.clone(), matcher }),
+                Err(e) => broken.push((responder.name.clone(), e)),
+            }
+        }
+        (Self { responders: compiled }, broken)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responders.is_empty()
+    }
+
+    /// The responders listening on `platform`/`channel` that `text` triggers,
+    /// in creation order, each with the text that matched. For "all keywords"
+    /// responders that is what the first keyword matched.
+    pub fn matches(&self, platform: &str, channel: &str, text: &str) -> Vec<(&Responder, String)> {
+        self.responders.iter()
+            .filter(|r| listens_on(&r.responder, platform, channel))
+            .filter_map(|r| {
+                let matched = match &r.matcher {
+                    Matcher::Pattern(re) => re.find(text).map(|m| m.as_str().to_string()),
+                    Matcher::AllOf(all) => all.iter()
+                        .map(|re| re.find(text).map(|m| m.as_str().to_string()))
+                        .collect::<Option<Vec<_>>>()
+                        .and_then(|found| found.into_iter().next()),
+                }?;
+                Some((&r.responder, matched))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn responder(name: &str, match_kind: ResponderMatch, pattern: &str) -> Responder {
+        Responder {
+            responder_id: Uuid::new_v4(),
+            name: name.to_string(),
+            match_kind,
+            pattern: pattern.to_string(),
+            responses: vec!["hi {user}".to_string()],
+            platform: None,
+            channel: None,
+            chance_percent: 100,
+            cooldown_secs: 0,
+            user_cooldown_secs: 0,
+            enabled: true,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_matches_keywords_regexes_and_filters() {
+        let mut discord_only = responder("gn", ResponderMatch::AnyKeyword, "good night|gn");
+        discord_only.platform = Some("discord".to_string());
+        let (set, broken) = ResponderSet::compile(&[
+            responder("greeting", ResponderMatch::AnyKeyword, "hello|hi|o7"),
+            responder("lurk", ResponderMatch::AllKeywords, "going|lurk"),
+            responder("ask", ResponderMatch::Regex, r"^when (is|does) the stream"),
+            discord_only,
+            responder("bad", ResponderMatch::Regex, "(unclosed"),
+        ]);
+        assert_eq!(broken.len(), 1);
+
+        let names = |text: &str, platform: &str| set.matches(platform, "#Chan", text)
+            .into_iter()
+            .map(|(r, _)| r.name.clone())
+            .collect::<Vec<_>>();
+
+        // Keywords match whole words, case-insensitively
+        assert_eq!(names("Hello there", "twitch-irc"), vec!["greeting"]);
+        assert!(names("this is high up", "twitch-irc").is_empty());
+        assert_eq!(set.matches("kick", "chan", "o7 all")[0].1, "o7");
+
+        assert!(names("going to bed", "twitch-irc").is_empty());
+        assert_eq!(names("lurk mode, going afk", "twitch-irc"), vec!["lurk"]);
+        assert_eq!(names("When does the stream start?", "kick"), vec!["ask"]);
+
+        assert!(names("gn chat", "twitch-irc").is_empty());
+        assert_eq!(names("gn chat", "discord"), vec!["gn"]);
+    }
+}
@@ -0,0 +1,447 @@
+// File: maowbot-core/src/services/responders/mod.rs
+//
+// Chat responders: regexes or keyword sets that answer matching chat lines,
+// kept apart from the prefix-based command service. Each responder has a
+// chance to answer, a cooldown for the whole channel and one per chatter, and
+// optional platform and channel filters. The first responder a line triggers
+// answers with one of its responses, picked at random, and
+// `BotEvent::ResponderTriggered` goes out so pipelines can do more.
+
+pub mod matcher;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::models::responder::{Responder, ResponderMatch};
+use maowbot_common::traits::repository_traits::{CredentialsRepository, ResponderRepository};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::i18n::render;
+use crate::platforms::manager::PlatformManager;
+use crate::services::chat_load;
+use crate::services::message_dedupe::MessageDedupe;
+use crate::services::message_sender::MessageSender;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use self::matcher::{validate_pattern, ResponderSet};
+
+const PLATFORMS: &[&str] = &["twitch-irc", "discord", "kick"];
+
+/// What's needed to add a responder.
+#[derive(Debug, Clone)]
+pub struct NewResponder {
+    pub name: String,
+    pub match_kind: ResponderMatch,
+    pub pattern: String,
+    pub responses: Vec<String>,
+    pub platform: Option<String>,
+    pub channel: Option<String>,
+    pub chance_percent: i32,
+    pub cooldown_secs: i32,
+    pub user_cooldown_secs: i32,
+}
+
+/// Changes to a responder; `None` leaves a field as it is. An empty platform
+/// or channel clears that filter.
+#[derive(Debug, Clone, Default)]
+pub struct ResponderEdit {
+    pub match_kind: Option<ResponderMatch>,
+    pub pattern: Option<String>,
+    pub responses: Option<Vec<String>>,
+    pub platform: Option<String>,
+    pub channel: Option<String>,
+    pub chance_percent: Option<i32>,
+    pub cooldown_secs: Option<i32>,
+    pub user_cooldown_secs: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// A responder a test message would trigger, ignoring chance and cooldowns.
+#[derive(Debug, Clone)]
+pub struct ResponderTest {
+    pub name: String,
+    pub matched: String,
+    /// One of its responses, filled in; None when it only publishes the event
+    pub response: Option<String>,
+}
+
+#[derive(Default)]
+struct ResponderState {
+    /// When each responder last answered
+    last_fired: HashMap<Uuid, DateTime<Utc>>,
+    /// When each responder last answered a chatter, by (responder, platform user id)
+    last_fired_for: HashMap<(Uuid, String), DateTime<Utc>>,
+}
+
+impl ResponderState {
+    fn cooling_down(&self, responder: &Responder, user_id: &str, now: DateTime<Utc>) -> bool {
+        let within = |at: Option<&DateTime<Utc>>, secs: i32| {
+            secs > 0 && at.is_some_and(|at| now - *at < Duration::seconds(secs as i64))
+        };
+        within(self.last_fired.get(&responder.responder_id), responder.cooldown_secs)
+            || within(
+                self.last_fired_for.get(&(responder.responder_id, user_id.to_string())),
+                responder.user_cooldown_secs,
+            )
+    }
+
+    fn fired(&mut self, responder: &Responder, user_id: &str, now: DateTime<Utc>) {
+        self.last_fired.insert(responder.responder_id, now);
+        if responder.user_cooldown_secs > 0 {
+            self.last_fired_for.insert((responder.responder_id, user_id.to_string()), now);
+        }
+    }
+}
+
+pub struct ResponderService {
+    repo: Arc<dyn ResponderRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    responders: RwLock<Arc<ResponderSet>>,
+    state: Mutex<ResponderState>,
+    seen: MessageDedupe,
+}
+
+fn validate_chance(percent: i32) -> Result<i32, Error> {
+    if (1..=100).contains(&percent) {
+        Ok(percent)
+    } else {
+        Err(Error::Parse("Chance must be between 1 and 100 percent".into()))
+    }
+}
+
+fn validate_cooldown(secs: i32) -> Result<i32, Error> {
+    if secs >= 0 {
+        Ok(secs)
+    } else {
+        Err(Error::Parse("Cooldowns can't be negative".into()))
+    }
+}
+
+/// None for "any platform"; otherwise one of the chat platforms.
+fn validate_platform(platform: Option<&str>) -> Result<Option<String>, Error> {
+    match platform.map(|p| p.trim().to_lowercase()).filter(|p| !p.is_empty() && p != "any") {
+        None => Ok(None),
+        Some(p) if PLATFORMS.contains(&p.as_str()) => Ok(Some(p)),
+        Some(p) => Err(Error::Parse(format!("Unknown platform '{}', expected one of {}", p, PLATFORMS.join(", ")))),
+    }
+}
+
+fn clean_channel(channel: Option<&str>) -> Option<String> {
+    channel.map(str::trim).filter(|c| !c.is_empty()).map(str::to_string)
+}
+
+fn clean_responses(responses: Vec<String>) -> Vec<String> {
+    responses.into_iter()
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+/// One of `responder`'s responses at random, filled in.
+fn pick_response(responder: &Responder, user: &str, channel: &str, matched: &str) -> Option<String> {
+    if responder.responses.is_empty() {
+        return None;
+    }
+    let template = &responder.responses[rand::rng().random_range(0..responder.responses.len())];
+    Some(render(template, &[
+        ("user", user),
+        ("channel", channel.trim_start_matches('#')),
+        ("match", matched),
+    ]))
+}
+
+impl ResponderService {
+    pub fn new(
+        repo: Arc<dyn ResponderRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            repo,
+            credentials_repo,
+            platform_manager,
+            event_bus,
+            settings,
+            responders: RwLock::new(Arc::new(ResponderSet::default())),
+            state: Mutex::new(ResponderState::default()),
+            seen: MessageDedupe::default(),
+        }
+    }
+
+    /// Loads the responders, then checks every chat line on every platform.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.reload().await {
+                warn!("[Responders] could not load responders: {:?}", e);
+            }
+
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Responders] event loop stopped");
+        });
+    }
+
+    /// Recompiles the responder set from the database.
+    async fn reload(&self) -> Result<(), Error> {
+        let responders = self.repo.list_responders().await?;
+        let (set, broken) = ResponderSet::compile(&responders);
+        for (name, e) in broken {
+            warn!("[Responders] '{}' is skipped: {}", name, e);
+        }
+        *self.responders.write() = Arc::new(set);
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, text, metadata, .. } = event else { return };
+        if !self.settings.get_bool("responders.enabled").unwrap_or(true) {
+            return;
+        }
+        // Commands belong to the command service, and nobody reads load test chat
+        if text.trim_start().starts_with('!') || chat_load::is_generated(&metadata) {
+            return;
+        }
+        let responders = self.responders.read().clone();
+        if responders.is_empty() {
+            return;
+        }
+
+        let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let message_id = field("message_id");
+        if !message_id.is_empty() && !self.seen.first_sighting(&message_id) {
+            return;
+        }
+        let matches = responders.matches(&platform, &channel, &text);
+        if matches.is_empty() {
+            return;
+        }
+        let (user_id, username) = (field("platform_user_id"), field("username"));
+        if let Some(guard) = self.platform_manager.outbound_guard() {
+            let name = Some(username.as_str()).filter(|n| !n.is_empty());
+            if guard.is_own_account(&Platform::from_string(&platform), &user_id, name).await {
+                return;
+            }
+        }
+
+        // The first responder off cooldown that wins its roll answers
+        let now = Utc::now();
+        let fired = {
+            let mut state = self.state.lock();
+            let mut rng = rand::rng();
+            let fired = matches.into_iter().find(|(responder, _)| {
+                !state.cooling_down(responder, &user_id, now)
+                    && rng.random_range(1..=100) <= responder.chance_percent
+            });
+            if let Some((responder, _)) = &fired {
+                state.fired(responder, &user_id, now);
+            }
+            fired.map(|(responder, matched)| (responder.clone(), matched))
+        };
+        let Some((responder, matched)) = fired else { return };
+        info!("[Responders] '{}' answers {} on {} {}", responder.name, username, platform, channel);
+
+        let response = pick_response(&responder, &username, &channel, &matched);
+        if let Some(line) = &response {
+            if let Err(e) = self.send(&platform, &channel, &field("guild_id"), line).await {
+                warn!("[Responders] could not answer on {} {}: {:?}", platform, channel, e);
+            }
+        }
+        if let Err(e) = self.repo.record_hit(responder.responder_id, now).await {
+            warn!("[Responders] could not count hit of '{}': {:?}", responder.name, e);
+        }
+        self.event_bus.publish(BotEvent::ResponderTriggered {
+            platform,
+            channel,
+            user: username,
+            platform_user_id: user_id,
+            responder: responder.name,
+            matched,
+            text,
+            response,
+            timestamp: now,
+        }).await;
+    }
+
+    /// Sends `line` back to where the message came from, as the bot account.
+    async fn send(&self, platform: &str, channel: &str, guild_id: &str, line: &str) -> Result<(), Error> {
+        match platform {
+            "twitch-irc" => {
+                MessageSender::new(self.credentials_repo.clone(), self.platform_manager.clone())
+                    .send_twitch_message(channel, line, None, Uuid::nil())
+                    .await
+            }
+            "discord" => {
+                let creds = self.credentials_repo.list_credentials_for_platform(&Platform::Discord).await?;
+                let bot = creds.iter().find(|c| c.is_bot)
+                    .ok_or_else(|| Error::Platform("No Discord bot credential".into()))?;
+                self.platform_manager.send_discord_message(&bot.user_name, guild_id, channel, line).await
+            }
+            "kick" => {
+                let creds = self.credentials_repo.list_credentials_for_platform(&Platform::Kick).await?;
+                let cred = creds.iter().find(|c| c.is_bot)
+                    .or_else(|| creds.iter().find(|c| c.is_broadcaster))
+                    .ok_or_else(|| Error::Platform("No Kick credential".into()))?;
+                self.platform_manager.send_kick_message(&cred.user_name, channel, line).await
+            }
+            other => Err(Error::Platform(format!("Responders can't answer on {}", other))),
+        }
+    }
+
+    pub async fn add_responder(&self, new: NewResponder) -> Result<Responder, Error> {
+        let name = new.name.trim().to_string();
+        if name.is_empty() {
+            return Err(Error::Parse("A responder needs a name".into()));
+        }
+        if self.repo.get_responder_by_name(&name).await?.is_some() {
+            return Err(Error::Parse(format!("A responder named '{}' already exists", name)));
+        }
+        validate_pattern(new.match_kind, &new.pattern)?;
+        let now = Utc::now();
+        let responder = Responder {
+            responder_id: Uuid::new_v4(),
+            name,
+            match_kind: new.match_kind,
+            pattern: new.pattern.trim().to_string(),
+            responses: clean_responses(new.responses),
+            platform: validate_platform(new.platform.as_deref())?,
+            channel: clean_channel(new.channel.as_deref()),
+            chance_percent: validate_chance(new.chance_percent)?,
+            cooldown_secs: validate_cooldown(new.cooldown_secs)?,
+            user_cooldown_secs: validate_cooldown(new.user_cooldown_secs)?,
+            enabled: true,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.repo.create_responder(&responder).await?;
+        self.reload().await?;
+        info!("[Responders] added {} responder '{}'", responder.match_kind, responder.name);
+        Ok(responder)
+    }
+
+    pub async fn edit_responder(&self, name: &str, edit: ResponderEdit) -> Result<Responder, Error> {
+        let mut responder = self.get_responder(name).await?;
+        let match_kind = edit.match_kind.unwrap_or(responder.match_kind);
+        let pattern = edit.pattern.map(|p| p.trim().to_string()).unwrap_or_else(|| responder.pattern.clone());
+        if match_kind != responder.match_kind || pattern != responder.pattern {
+            validate_pattern(match_kind, &pattern)?;
+        }
+        responder.match_kind = match_kind;
+        responder.pattern = pattern;
+        if let Some(responses) = edit.responses {
+            responder.responses = clean_responses(responses);
+        }
+        if let Some(platform) = edit.platform {
+            responder.platform = validate_platform(Some(&platform))?;
+        }
+        if let Some(channel) = edit.channel {
+            responder.channel = clean_channel(Some(&channel));
+        }
+        if let Some(percent) = edit.chance_percent {
+            responder.chance_percent = validate_chance(percent)?;
+        }
+        if let Some(secs) = edit.cooldown_secs {
+            responder.cooldown_secs = validate_cooldown(secs)?;
+        }
+        if let Some(secs) = edit.user_cooldown_secs {
+            responder.user_cooldown_secs = validate_cooldown(secs)?;
+        }
+        responder.enabled = edit.enabled.unwrap_or(responder.enabled);
+        responder.updated_at = Utc::now();
+        self.repo.update_responder(&responder).await?;
+        self.reload().await?;
+        Ok(responder)
+    }
+
+    pub async fn delete_responder(&self, name: &str) -> Result<(), Error> {
+        let responder = self.get_responder(name).await?;
+        self.repo.delete_responder(responder.responder_id).await?;
+        self.reload().await?;
+        info!("[Responders] deleted '{}'", responder.name);
+        Ok(())
+    }
+
+    pub async fn get_responder(&self, name: &str) -> Result<Responder, Error> {
+        self.repo.get_responder_by_name(name.trim()).await?
+            .ok_or_else(|| Error::NotFound(format!("No responder named '{}'", name.trim())))
+    }
+
+    pub async fn list_responders(&self) -> Result<Vec<Responder>, Error> {
+        self.repo.list_responders().await
+    }
+
+    /// Every enabled responder `text` would trigger on `platform`/`channel`,
+    /// ignoring chance and cooldowns, without answering.
+    pub fn test(&self, platform: &str, channel: &str, text: &str, user: &str) -> Vec<ResponderTest> {
+        self.responders.read()
+            .matches(platform, channel, text)
+            .into_iter()
+            .map(|(responder, matched)| ResponderTest {
+                name: responder.name.clone(),
+                response: pick_response(responder, user, channel, &matched),
+                matched,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldowns_apply_per_responder_and_per_chatter() {
+        let now = Utc::now();
+        let responder = Responder {
+            responder_id: Uuid::new_v4(),
+            name: "hi".to_string(),
+            match_kind: ResponderMatch::AnyKeyword,
+            pattern: "hi".to_string(),
+            responses: Vec::new(),
+            platform: None,
+            channel: None,
+            chance_percent: 100,
+            cooldown_secs: 10,
+            user_cooldown_secs: 60,
+            enabled: true,
+            hit_count: 0,
+            last_hit_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let mut state = ResponderState::default();
+        assert!(!state.cooling_down(&responder, "a", now));
+        state.fired(&responder, "a", now);
+
+        assert!(state.cooling_down(&responder, "b", now + Duration::seconds(5)));
+        assert!(!state.cooling_down(&responder, "b", now + Duration::seconds(15)));
+        assert!(state.cooling_down(&responder, "a", now + Duration::seconds(15)));
+        assert!(!state.cooling_down(&responder, "a", now + Duration::seconds(61)));
+    }
+}
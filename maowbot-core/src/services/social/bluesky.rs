@@ -0,0 +1,117 @@
+use chrono::Utc;
+use reqwest::Client as ReqwestClient;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::Error;
+
+/// Minimal ATProto client: log in with an app password and create posts.
+pub struct BlueskyClient {
+    http: ReqwestClient,
+    service: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlueskySession {
+    pub access_jwt: String,
+    pub did: String,
+    pub handle: String,
+}
+
+#[derive(Deserialize)]
+struct CreateRecordResponse {
+    uri: String,
+}
+
+impl BlueskyClient {
+    pub fn new(service: &str) -> Self {
+        Self {
+            http: ReqwestClient::new(),
+            service: service.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// `com.atproto.server.createSession`; `identifier` is a handle or DID.
+    pub async fn create_session(&self, identifier: &str, app_password: &str) -> Result<BlueskySession, Error> {
+        self.http
+            .post(format!("{}/xrpc/com.atproto.server.createSession", self.service))
+            .json(&json!({ "identifier": identifier, "password": app_password }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Auth(format!("Bluesky login failed: {e}")))?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Posts `text` as the session's account and returns the post's bsky.app URL.
+    pub async fn create_post(&self, session: &BlueskySession, text: &str) -> Result<Option<String>, Error> {
+        let mut record = json!({
+            "$type": "app.bsky.feed.post",
+            "text": text,
+            "createdAt": Utc::now().to_rfc3339(),
+        });
+        let facets = link_facets(text);
+        if !facets.is_empty() {
+            record["facets"] = Value::Array(facets);
+        }
+
+        let resp: CreateRecordResponse = self.http
+            .post(format!("{}/xrpc/com.atproto.repo.createRecord", self.service))
+            .bearer_auth(&session.access_jwt)
+            .json(&json!({
+                "repo": session.did,
+                "collection": "app.bsky.feed.post",
+                "record": record,
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Platform(format!("Bluesky post failed: {e}")))?
+            .json()
+            .await?;
+        debug!("Bluesky post created: {}", resp.uri);
+
+        // at://did/app.bsky.feed.post/<rkey>
+        Ok(resp.uri.rsplit('/').next()
+            .map(|rkey| format!("https://bsky.app/profile/{}/post/{}", session.handle, rkey)))
+    }
+}
+
+/// Bluesky doesn't linkify plain text, so every http(s) URL needs a link facet
+/// spanning its UTF-8 byte range.
+pub fn link_facets(text: &str) -> Vec<Value> {
+    let mut facets = Vec::new();
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += word.len();
+        let url = word.trim_end().trim_end_matches(['.', ',', '!', '?', ')', ';', ':']);
+        if url.starts_with("https://") || url.starts_with("http://") {
+            facets.push(json!({
+                "index": { "byteStart": start, "byteEnd": start + url.len() },
+                "features": [{ "$type": "app.bsky.richtext.facet#link", "uri": url }],
+            }));
+        }
+    }
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_facets_use_byte_offsets() {
+        let text = "Live now ✨ https://twitch.tv/maow! come hang";
+        let facets = link_facets(text);
+        assert_eq!(facets.len(), 1);
+        let start = text.find("https").unwrap();
+        assert_eq!(facets[0]["index"]["byteStart"], start);
+        assert_eq!(facets[0]["index"]["byteEnd"], start + "https://twitch.tv/maow".len());
+        assert_eq!(facets[0]["features"][0]["uri"], "https://twitch.tv/maow");
+    }
+}
@@ -0,0 +1,51 @@
+use reqwest::Client as ReqwestClient;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::debug;
+
+use crate::Error;
+
+/// Posts statuses with a personal access token (`write:statuses` scope).
+pub struct MastodonClient {
+    http: ReqwestClient,
+    instance: String,
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct StatusResponse {
+    id: String,
+    url: Option<String>,
+}
+
+impl MastodonClient {
+    pub fn new(instance: &str, access_token: &str) -> Self {
+        let instance = instance.trim().trim_end_matches('/');
+        let instance = if instance.starts_with("http://") || instance.starts_with("https://") {
+            instance.to_string()
+        } else {
+            format!("https://{instance}")
+        };
+        Self {
+            http: ReqwestClient::new(),
+            instance,
+            access_token: access_token.to_string(),
+        }
+    }
+
+    /// `POST /api/v1/statuses`; returns the status URL.
+    pub async fn post_status(&self, text: &str, visibility: &str) -> Result<Option<String>, Error> {
+        let resp: StatusResponse = self.http
+            .post(format!("{}/api/v1/statuses", self.instance))
+            .bearer_auth(&self.access_token)
+            .json(&json!({ "status": text, "visibility": visibility.to_lowercase() }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::Platform(format!("Mastodon post failed: {e}")))?
+            .json()
+            .await?;
+        debug!("Mastodon status created: {}", resp.id);
+        Ok(resp.url)
+    }
+}
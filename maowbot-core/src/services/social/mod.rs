@@ -0,0 +1,121 @@
+// File: maowbot-core/src/services/social/mod.rs
+//
+// Posting statuses to Bluesky and Mastodon. Credentials live in bot_config
+// under `social.*` (see settings/definitions.rs) so they can be set with
+// `config set`; the app password and access token are secret settings, kept
+// encrypted and left out of listings.
+
+pub mod bluesky;
+pub mod mastodon;
+
+use std::fmt;
+use std::str::FromStr;
+
+use maowbot_common::traits::repository_traits::BotConfigRepository;
+
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+pub use bluesky::BlueskyClient;
+pub use mastodon::MastodonClient;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocialNetwork {
+    Bluesky,
+    Mastodon,
+}
+
+impl fmt::Display for SocialNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SocialNetwork::Bluesky => write!(f, "bluesky"),
+            SocialNetwork::Mastodon => write!(f, "mastodon"),
+        }
+    }
+}
+
+impl FromStr for SocialNetwork {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "bluesky" | "bsky" => Ok(SocialNetwork::Bluesky),
+            "mastodon" => Ok(SocialNetwork::Mastodon),
+            other => Err(Error::ValidationError(format!("Unknown social network '{}'", other))),
+        }
+    }
+}
+
+/// A status that was published, with a link to it when the network returns one.
+#[derive(Debug, Clone)]
+pub struct PostedStatus {
+    pub network: SocialNetwork,
+    pub url: Option<String>,
+}
+
+/// Reads a `social.*` value, decrypting secret ones, falling back to the setting's default.
+async fn setting(repo: &dyn BotConfigRepository, key: &str) -> Result<Option<String>, Error> {
+    let stored = if SettingsRegistry::is_secret(key) {
+        repo.get_secret(key).await?
+    } else {
+        repo.get_value(key).await?
+    };
+    let stored = stored.filter(|v| !v.trim().is_empty());
+    Ok(stored.or_else(|| {
+        SettingsRegistry::definition(key)
+            .and_then(|d| d.default)
+            .map(str::to_string)
+    }))
+}
+
+async fn required(repo: &dyn BotConfigRepository, key: &str) -> Result<String, Error> {
+    setting(repo, key).await?
+        .ok_or_else(|| Error::ValidationError(format!("'{}' is not set (use `config set {} <value>`)", key, key)))
+}
+
+/// Publishes `text` on `network` with the credentials currently in bot_config.
+pub async fn post_status(
+    repo: &dyn BotConfigRepository,
+    network: SocialNetwork,
+    text: &str,
+) -> Result<PostedStatus, Error> {
+    let url = match network {
+        SocialNetwork::Bluesky => {
+            let client = BlueskyClient::new(&required(repo, "social.bluesky.service").await?);
+            let session = client.create_session(
+                &required(repo, "social.bluesky.handle").await?,
+                &required(repo, "social.bluesky.app_password").await?,
+            ).await?;
+            client.create_post(&session, text).await?
+        }
+        SocialNetwork::Mastodon => {
+            let client = MastodonClient::new(
+                &required(repo, "social.mastodon.instance").await?,
+                &required(repo, "social.mastodon.access_token").await?,
+            );
+            let visibility = required(repo, "social.mastodon.visibility").await?;
+            client.post_status(text, &visibility).await?
+        }
+    };
+    Ok(PostedStatus { network, url })
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use crate::repositories::memory::InMemoryBotConfigRepository;
+
+    #[tokio::test]
+    async fn test_secret_credentials_are_read_encrypted() {
+        let repo = InMemoryBotConfigRepository::new();
+        repo.set_value("social.mastodon.instance", "https://mastodon.social").await.unwrap();
+        repo.set_secret("social.mastodon.access_token", "abc").await.unwrap();
+        assert_eq!(setting(&repo, "social.mastodon.instance").await.unwrap().as_deref(), Some("https://mastodon.social"));
+        assert_eq!(setting(&repo, "social.mastodon.access_token").await.unwrap().as_deref(), Some("abc"));
+
+        // A plain-text row isn't a secret setting's value
+        repo.set_value("social.bluesky.app_password", "hunter2").await.unwrap();
+        assert_eq!(setting(&repo, "social.bluesky.app_password").await.unwrap(), None);
+        assert_eq!(setting(&repo, "social.bluesky.service").await.unwrap().as_deref(), Some("https://bsky.social"));
+    }
+}
@@ -0,0 +1,236 @@
+// File: maowbot-core/src/services/twitch/bot_detection.rs
+//
+// Scores new chatters in the broadcaster's Twitch chat for how likely they are
+// bot accounts. A chatter counts as new when the bot first saw them recently;
+// once they've sent a few messages (or a couple of minutes have passed) their
+// account age and follow date are looked up on Helix and combined with their
+// messages by `score_bot`. The score is stored on the user's analysis, where
+// the viewer card shows it, and handed to raid protection for its likely-bot
+// check.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::user_analysis::{score_bot, BotEvidence, BotScore, UserAnalysis};
+use maowbot_common::traits::repository_traits::UserAnalysisRepository;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::plugins::manager::PluginManager;
+use crate::services::twitch::broadcaster_channel::BroadcasterChannel;
+use crate::services::twitch::broadcaster_helix;
+use crate::services::twitch::protection_service::ProtectionService;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// Chatters scored per tick, to keep Helix calls down during a raid.
+const MAX_SCORED_PER_TICK: usize = 25;
+/// Chatters waiting for enough messages; the oldest are dropped past this.
+const MAX_PENDING: usize = 2000;
+/// The set of already handled chatters is dropped once it grows past this.
+const MAX_HANDLED: usize = 50_000;
+/// Chatters are scored this long after their first message at the latest.
+const MAX_WAIT_SECS: i64 = 120;
+const TICK_SECS: u64 = 5;
+
+struct PendingChatter {
+    user_id: Uuid,
+    username: String,
+    first_message_at: DateTime<Utc>,
+    messages: Vec<String>,
+}
+
+#[derive(Default)]
+struct DetectionState {
+    /// By Twitch user id
+    pending: HashMap<String, PendingChatter>,
+    /// Twitch user ids scored or skipped since the bot started
+    handled: HashSet<String>,
+}
+
+pub struct BotDetectionService {
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    protection: Arc<ProtectionService>,
+    /// The channel we watch
+    channel: BroadcasterChannel,
+    state: Mutex<DetectionState>,
+}
+
+impl BotDetectionService {
+    pub fn new(
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+        protection: Arc<ProtectionService>,
+    ) -> Self {
+        Self {
+            event_bus,
+            settings,
+            plugin_manager,
+            protection,
+            channel: BroadcasterChannel::new(),
+            state: Mutex::new(DetectionState::default()),
+        }
+    }
+
+    /// Watches chat and scores chatters that are due every few seconds.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(TICK_SECS));
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event),
+                        None => break,
+                    },
+                    _ = interval.tick() => service.tick().await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[BotDetection] event loop stopped");
+        });
+    }
+
+    fn enabled(&self) -> bool {
+        self.settings.get_bool("bot_detection.enabled").unwrap_or(true)
+    }
+
+    fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, user, text, timestamp, metadata } = event else { return };
+        if platform != "twitch-irc" || !self.enabled() {
+            return;
+        }
+        let Ok(user_id) = Uuid::parse_str(&user) else { return };
+        let Some(platform_user_id) = metadata.get("platform_user_id").and_then(|v| v.as_str()) else { return };
+        let sample = self.settings.get_i64("bot_detection.sample_messages").unwrap_or(3).max(1) as usize;
+
+        if !self.channel.is(&channel) {
+            return;
+        }
+        let mut state = self.state.lock();
+        if state.handled.contains(platform_user_id) {
+            return;
+        }
+        if !state.pending.contains_key(platform_user_id) && state.pending.len() >= MAX_PENDING {
+            let oldest = state.pending.iter()
+                .min_by_key(|(_, c)| c.first_message_at)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                state.pending.remove(&oldest);
+            }
+        }
+        let chatter = state.pending.entry(platform_user_id.to_string()).or_insert_with(|| PendingChatter {
+            user_id,
+            username: metadata.get("username").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            first_message_at: timestamp,
+            messages: Vec::new(),
+        });
+        if chatter.messages.len() < sample {
+            chatter.messages.push(text);
+        }
+    }
+
+    async fn tick(&self) {
+        let now = Utc::now();
+        self.channel.resolve(&*self.plugin_manager.credentials_repo).await;
+        if !self.enabled() {
+            self.state.lock().pending.clear();
+            return;
+        }
+
+        let sample = self.settings.get_i64("bot_detection.sample_messages").unwrap_or(3).max(1) as usize;
+        let due: Vec<(String, PendingChatter)> = {
+            let mut state = self.state.lock();
+            let ids: Vec<String> = state.pending.iter()
+                .filter(|(_, c)| c.messages.len() >= sample || now - c.first_message_at >= Duration::seconds(MAX_WAIT_SECS))
+                .map(|(id, _)| id.clone())
+                .take(MAX_SCORED_PER_TICK)
+                .collect();
+            if state.handled.len() > MAX_HANDLED {
+                state.handled.clear();
+            }
+            ids.into_iter()
+                .filter_map(|id| {
+                    let chatter = state.pending.remove(&id)?;
+                    state.handled.insert(id.clone());
+                    Some((id, chatter))
+                })
+                .collect()
+        };
+        if due.is_empty() {
+            return;
+        }
+
+        let new_since = now - Duration::hours(self.settings.get_i64("bot_detection.new_chatter_hours").unwrap_or(24));
+        let mut new_chatters = Vec::new();
+        for (id, chatter) in due {
+            match self.plugin_manager.user_repo.get(chatter.user_id).await {
+                Ok(Some(user)) if user.created_at >= new_since => new_chatters.push((id, chatter)),
+                Ok(_) => {}
+                Err(e) => debug!("[BotDetection] could not load user {}: {:?}", chatter.user_id, e),
+            }
+        }
+        if new_chatters.is_empty() {
+            return;
+        }
+
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await;
+        let ids: Vec<String> = new_chatters.iter().map(|(id, _)| id.clone()).collect();
+        let created = match &helix {
+            Ok(helix) => helix.client.fetch_account_created(&ids).await.unwrap_or_else(|e| {
+                debug!("[BotDetection] account age lookup failed: {:?}", e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+
+        for (id, chatter) in new_chatters {
+            let followed_at = match &helix {
+                Ok(helix) => helix.client.fetch_follow_date(&id, &helix.broadcaster_id).await.ok().flatten(),
+                Err(_) => None,
+            };
+            let result = score_bot(&BotEvidence {
+                username: chatter.username.clone(),
+                account_created: created.get(&id).copied(),
+                followed_at,
+                first_message_at: chatter.first_message_at,
+                messages: chatter.messages,
+            });
+            if result.score > 0.0 {
+                info!("[BotDetection] {} scored {:.2}: {}", chatter.username, result.score, result.signals.join(", "));
+            }
+            self.protection.note_bot_score(&id, result.score);
+            if let Err(e) = self.save(chatter.user_id, result, now).await {
+                warn!("[BotDetection] could not save score for {}: {:?}", chatter.user_id, e);
+            }
+        }
+    }
+
+    async fn save(&self, user_id: Uuid, result: BotScore, now: DateTime<Utc>) -> Result<(), Error> {
+        let repo = &self.plugin_manager.user_analysis_repo;
+        let mut analysis = match repo.get_analysis(user_id).await? {
+            Some(analysis) => analysis,
+            None => {
+                let analysis = UserAnalysis::new(user_id);
+                repo.create_analysis(&analysis).await?;
+                analysis
+            }
+        };
+        analysis.bot_score = result.score;
+        analysis.bot_signals = result.signals;
+        analysis.bot_scored_at = Some(now);
+        repo.update_analysis(&analysis).await
+    }
+}
@@ -0,0 +1,103 @@
+// File: maowbot-core/src/services/twitch/broadcaster_channel.rs
+//
+// The broadcaster's Twitch login, i.e. the channel services like moderation,
+// protection and bot detection watch. It's unknown until a broadcaster
+// credential exists, so a failed lookup is retried now and then.
+
+use std::future::Future;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use super::broadcaster_helix;
+
+/// How long to wait before asking for the broadcaster's login again.
+const LOOKUP_RETRY_SECS: i64 = 60;
+
+#[derive(Default)]
+struct Lookup {
+    login: Option<String>,
+    next_attempt: Option<DateTime<Utc>>,
+}
+
+/// The broadcaster's login once known, lowercase.
+#[derive(Default)]
+pub struct BroadcasterChannel {
+    lookup: Mutex<Lookup>,
+}
+
+impl BroadcasterChannel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The login if it's known, without asking for it.
+    pub fn get(&self) -> Option<String> {
+        self.lookup.lock().login.clone()
+    }
+
+    /// True when `channel` (with or without '#') is the broadcaster's.
+    pub fn is(&self, channel: &str) -> bool {
+        self.lookup.lock().login.as_deref()
+            .is_some_and(|login| login.eq_ignore_ascii_case(channel.trim_start_matches('#')))
+    }
+
+    /// The login, looking it up first while unknown (at most once a minute).
+    pub async fn resolve(&self, credentials_repo: &(dyn CredentialsRepository + Send + Sync)) -> Option<String> {
+        self.resolve_at(Utc::now(), || async {
+            broadcaster_helix(credentials_repo).await.map(|helix| helix.login).ok()
+        }).await
+    }
+
+    async fn resolve_at<F, Fut>(&self, now: DateTime<Utc>, look_up: F) -> Option<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<String>>,
+    {
+        {
+            let lookup = self.lookup.lock();
+            if lookup.login.is_some() || lookup.next_attempt.is_some_and(|at| now < at) {
+                return lookup.login.clone();
+            }
+        }
+        let login = look_up().await.map(|l| l.to_lowercase());
+        let mut lookup = self.lookup.lock();
+        lookup.next_attempt = Some(now + Duration::seconds(LOOKUP_RETRY_SECS));
+        lookup.login = login.clone();
+        login
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[tokio::test]
+    async fn test_failed_lookups_are_retried_after_a_minute() {
+        let channel = BroadcasterChannel::new();
+        let lookups = Cell::new(0);
+        let look_up = |login: Option<&str>| {
+            lookups.set(lookups.get() + 1);
+            let login = login.map(str::to_string);
+            async move { login }
+        };
+        let start = Utc::now();
+
+        assert_eq!(channel.resolve_at(start, || look_up(None)).await, None);
+        // Too soon to ask again
+        assert_eq!(channel.resolve_at(start + Duration::seconds(30), || look_up(Some("x"))).await, None);
+        assert_eq!(lookups.get(), 1);
+
+        let found = channel.resolve_at(start + Duration::seconds(61), || look_up(Some("CatStreamer"))).await;
+        assert_eq!(found.as_deref(), Some("catstreamer"));
+        assert!(channel.is("#CatStreamer"));
+        assert!(!channel.is("someone_else"));
+
+        // A known login is never looked up again
+        let found = channel.resolve_at(start + Duration::days(1), || look_up(None)).await;
+        assert_eq!(found.as_deref(), Some("catstreamer"));
+        assert_eq!(lookups.get(), 2);
+    }
+}
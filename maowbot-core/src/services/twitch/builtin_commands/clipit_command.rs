@@ -0,0 +1,25 @@
+//! Built-in `!clipit` command: clips the live stream and replies with the
+//! link; the clip is posted to the Discord clips channel once it's processed.
+
+use maowbot_common::models::Command;
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+pub async fn handle_clipit(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    user: &User,
+    _args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.clip_service.clone()) else {
+        return Ok(ctx.text("clipit.unavailable", &[]));
+    };
+
+    let requested_by = user.global_username.as_deref().unwrap_or("chat");
+    match service.clip_now(requested_by).await {
+        Ok(url) => Ok(ctx.text("clipit.created", &[("url", &url)])),
+        Err(e) => Ok(ctx.text("clipit.failed", &[("error", &e.to_string())])),
+    }
+}
@@ -0,0 +1,45 @@
+//! Built-in `!emotestats [window]` command: the channel's most used emotes over
+//! a rolling window ("15m", "1h") or whole days ("today", "7d"). Defaults to the last hour.
+
+use maowbot_common::models::Command;
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::emote_stats::{StatsWindow, ROLLING_MINUTES};
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+/// Emotes listed in the reply.
+const TOP_EMOTES: usize = 5;
+
+pub async fn handle_emotestats(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    _user: &User,
+    args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.emote_stats_service.clone()) else {
+        return Ok(ctx.text("emotestats.unavailable", &[]));
+    };
+
+    let window = match args.get("window") {
+        None => StatsWindow::Minutes(ROLLING_MINUTES),
+        Some(arg) => match StatsWindow::parse(arg) {
+            Ok(window) => window,
+            Err(_) => return Ok(ctx.text("emotestats.bad_window", &[("window", arg)])),
+        },
+    };
+
+    let described = match window {
+        StatsWindow::Minutes(60) => ctx.text("emotestats.window.hour", &[]),
+        StatsWindow::Minutes(m) => ctx.text("emotestats.window.minutes", &[("count", &m.to_string())]),
+        StatsWindow::Days(1) => ctx.text("emotestats.window.today", &[]),
+        StatsWindow::Days(d) => ctx.text("emotestats.window.days", &[("count", &d.to_string())]),
+    };
+
+    let top = service.top_emotes(ctx.channel, window, TOP_EMOTES).await?;
+    if top.is_empty() {
+        return Ok(ctx.text("emotestats.none", &[("window", &described)]));
+    }
+    let list: Vec<String> = top.iter().map(|e| format!("{} x{}", e.emote_name, e.uses)).collect();
+    Ok(ctx.text("emotestats.top", &[("window", &described), ("emotes", &list.join(", "))]))
+}
@@ -5,6 +5,7 @@ use maowbot_common::models::platform::{Platform};
 use maowbot_common::models::user::User;
 use crate::Error;
 use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::services::command_spec::CommandArgs;
 use crate::services::twitch::command_service::CommandContext;
 
 /// The `handle_followage` function implements the `!followage` command.
@@ -15,7 +16,7 @@ pub async fn handle_followage(
     _cmd: &Command,
     ctx: &CommandContext<'_>,
     user: &User,
-    _raw_args: &str,
+    _args: &CommandArgs,
 ) -> Result<String, Error> {
     let user_name = user
         .global_username
@@ -39,11 +40,7 @@ pub async fn handle_followage(
     let broadcaster_cred = match broadcaster_cred_opt {
         Some(cred) => cred,
         None => {
-            return Ok(
-                "No broadcaster credential found for Twitch. \
-Please designate an is_broadcaster Twitch Helix account first."
-                    .to_string()
-            );
+            return Ok(ctx.text("followage.no_broadcaster", &[]));
         }
     };
 
@@ -53,11 +50,7 @@ Please designate an is_broadcaster Twitch Helix account first."
     let broadcaster_id = match broadcaster_cred.platform_id.clone() {
         Some(pid) if !pid.trim().is_empty() => pid,
         _ => {
-            return Ok(format!(
-                "Broadcaster credential for user_name='{}' has no .platform_id. \
-Cannot fetch follow info.",
-                broadcaster_cred.user_name
-            ));
+            return Ok(ctx.text("followage.no_broadcaster_id", &[("account", &broadcaster_cred.user_name)]));
         }
     };
 
@@ -74,10 +67,7 @@ Cannot fetch follow info.",
     let viewer_id = match viewer_identity_opt {
         Some(ident) => ident.platform_user_id.clone(),
         None => {
-            return Ok(format!(
-                "You have not linked any Twitch ID, {}. I cannot check your follow status.",
-                user_name
-            ));
+            return Ok(ctx.text("followage.not_linked", &[("user", user_name)]));
         }
     };
 
@@ -110,10 +100,7 @@ Cannot fetch follow info.",
     let follow_date = match follow_date_opt {
         Some(fd) => fd,
         None => {
-            return Ok(format!(
-                "{} is not following that channel (or data is unavailable).",
-                user_name
-            ));
+            return Ok(ctx.text("followage.not_following", &[("user", user_name)]));
         }
     };
 
@@ -129,21 +116,16 @@ Cannot fetch follow info.",
     // Shorter cases for <1 day or <1 month
     if total_days < 1 {
         let hours = diff.num_hours();
-        return Ok(format!(
-            "{} has been following for about {} hour(s).",
-            user_name, hours
-        ));
+        return Ok(ctx.text("followage.hours", &[("user", user_name), ("hours", &hours.to_string())]));
     }
     if months < 1 {
-        return Ok(format!(
-            "{} has been following for {} day(s).",
-            user_name, total_days
-        ));
+        return Ok(ctx.text("followage.days", &[("user", user_name), ("days", &total_days.to_string())]));
     }
 
     // If >=1 month, show months + leftover days
-    Ok(format!(
-        "{} has been following for {} month(s) and {} day(s).",
-        user_name, months, leftover_days
-    ))
+    Ok(ctx.text("followage.months", &[
+        ("user", user_name),
+        ("months", &months.to_string()),
+        ("days", &leftover_days.to_string()),
+    ]))
 }
@@ -0,0 +1,55 @@
+//! Built-in `!lastseen <user>` command: when the user last chatted in this channel,
+//! looked up in the chat archive.
+
+use chrono::Utc;
+use maowbot_common::models::Command;
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+/// Longest quoted message before it is cut off.
+const MAX_QUOTE_CHARS: usize = 120;
+
+pub async fn handle_lastseen(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    user: &User,
+    args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(target_name) = args.get("user") else {
+        return Ok(ctx.text("lastseen.usage", &[]));
+    };
+
+    let Some(pm) = &ctx.plugin_manager else {
+        return Ok(ctx.text("lastseen.unavailable", &[]));
+    };
+
+    let target = match ctx.user_service.find_user_by_global_username(target_name).await {
+        Ok(u) => u,
+        Err(_) => return Ok(ctx.text("lastseen.never", &[("user", target_name)])),
+    };
+    if target.user_id == user.user_id {
+        return Ok(ctx.text("lastseen.self", &[("user", target_name)]));
+    }
+
+    let Some(msg) = pm.chat_archive.last_message_for_user(target.user_id, None, Some(ctx.channel)).await? else {
+        return Ok(ctx.text("lastseen.never", &[("user", target_name)]));
+    };
+
+    let mut quote: String = msg.message_text.chars().take(MAX_QUOTE_CHARS).collect();
+    if msg.message_text.chars().count() > MAX_QUOTE_CHARS {
+        quote.push('…');
+    }
+
+    let diff = Utc::now().signed_duration_since(msg.timestamp);
+    let ago = if diff.num_days() >= 1 {
+        ctx.text("time.days", &[("count", &diff.num_days().to_string())])
+    } else if diff.num_hours() >= 1 {
+        ctx.text("time.hours", &[("count", &diff.num_hours().to_string())])
+    } else {
+        ctx.text("time.minutes", &[("count", &diff.num_minutes().max(1).to_string())])
+    };
+
+    Ok(ctx.text("lastseen.seen", &[("user", target_name), ("ago", &ago), ("message", &quote)]))
+}
@@ -0,0 +1,33 @@
+//! Built-in `!marker [description]` command: places a Twitch stream marker at
+//! the current point of the broadcast, which also becomes a VOD chapter.
+
+use maowbot_common::models::Command;
+use maowbot_common::models::stream_marker::{format_timestamp, MarkerSource};
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+pub async fn handle_marker(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    user: &User,
+    args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.stream_marker_service.clone()) else {
+        return Ok(ctx.text("marker.unavailable", &[]));
+    };
+
+    let description = match args.get("description").unwrap_or_default() {
+        "" => ctx.text("marker.default_description", &[("user", user.global_username.as_deref().unwrap_or("chat"))]),
+        desc => desc.to_string(),
+    };
+
+    match service.create_marker(MarkerSource::Command, &description).await {
+        Ok(marker) => Ok(ctx.text("marker.placed", &[
+            ("time", &format_timestamp(marker.position_seconds)),
+            ("description", &marker.description),
+        ])),
+        Err(e) => Ok(ctx.text("marker.failed", &[("error", &e.to_string())])),
+    }
+}
@@ -1,57 +1,208 @@
 // File: maowbot-core/src/services/builtin_commands/mod.rs
-//! Defines built-in commands such as `ping`, `followage`, `world`, `instance`, etc.
-//! Each command is in its own file, but we expose a single `handle_builtin_command`
-//! entry point that the CommandService can call.
+//! Defines built-in commands such as `ping`, `followage`, `lastseen`, `world`, `instance`, `whoshere`, `lurkers`, `schedule`, `toggle`, `set`, etc.
+//! Each command is in its own file; `BUILTIN_COMMANDS` holds the definition
+//! (arguments, default role, platforms) that chat and Discord slash commands share.
 
 pub mod ping_command;
 pub mod followage_command;
+pub mod lastseen_command;
 pub mod vrchat_commands;
 pub mod vanish;
+pub mod marker_command;
+pub mod emotestats_command;
+pub mod clipit_command;
+pub mod presence_commands;
+pub mod schedule_command;
+pub mod osc_commands;
 
 use maowbot_common::models::Command;
 use maowbot_common::models::user::User;
 use crate::Error;
+use crate::services::command_spec::{
+    ArgKind, ArgSpec, CommandArgs, CommandHandler, CommandSpec, EVERYWHERE, TWITCH_ONLY,
+};
 use crate::services::twitch::builtin_commands::{
     ping_command::handle_ping,
     followage_command::handle_followage,
-    vrchat_commands::{handle_world, handle_instance, handle_vrchat_online_offline},
+    lastseen_command::handle_lastseen,
+    marker_command::handle_marker,
+    emotestats_command::handle_emotestats,
+    clipit_command::handle_clipit,
+    presence_commands::{handle_lurkers, handle_watchtime},
+    schedule_command::handle_schedule,
+    osc_commands::{handle_toggle, handle_set},
+    vanish::handle_vanish,
+    vrchat_commands::{handle_world, handle_instance, handle_whoshere, handle_vrchat_online_offline},
 };
 use crate::services::twitch::command_service::CommandContext;
 
-pub async fn handle_builtin_command(
-    cmd: &Command,
-    ctx: &CommandContext<'_>,
-    user: &User,
-    raw_args: &str,
-) -> Result<Option<String>, Error> {
-    // The DB now stores 'ping', 'followage', etc. (no exclamation).
-    let cname = cmd.command_name.to_lowercase();
+/// Boxes an async handler into a `CommandHandler`.
+macro_rules! handler {
+    ($f:path) => {{
+        fn boxed<'a>(
+            cmd: &'a Command,
+            ctx: &'a CommandContext<'a>,
+            user: &'a User,
+            args: &'a CommandArgs,
+        ) -> futures_util::future::BoxFuture<'a, Result<String, Error>> {
+            Box::pin($f(cmd, ctx, user, args))
+        }
+        boxed as CommandHandler
+    }};
+}
 
-    if cname == "ping" {
-        let resp = handle_ping(cmd, ctx, user, raw_args).await?;
-        return Ok(Some(resp));
-    }
-    else if cname == "followage" {
-        let resp = handle_followage(cmd, ctx, user, raw_args).await?;
-        return Ok(Some(resp));
-    }
-    else if cname == "vanish" {
-        let resp = vanish::handle_vanish(cmd, ctx, user, raw_args).await?;
-        return Ok(Some(resp));
-    }
-    else if cname == "world" {
-        let resp = handle_world(cmd, ctx, user, raw_args).await?;
-        return Ok(Some(resp));
-    }
-    else if cname == "instance" {
-        let resp = handle_instance(cmd, ctx, user, raw_args).await?;
-        return Ok(Some(resp));
-    }
-    else if cname == "vrchat" {
-        let resp = handle_vrchat_online_offline(cmd, ctx, user, raw_args).await?;
-        return Ok(Some(resp));
-    }
+pub static BUILTIN_COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "ping",
+        description: "Checks that the bot is answering",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_ping),
+    },
+    CommandSpec {
+        name: "followage",
+        description: "How long you have been following the channel on Twitch",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_followage),
+    },
+    CommandSpec {
+        name: "lastseen",
+        description: "When someone last chatted here",
+        args: &[ArgSpec::required("user", ArgKind::User, "Who to look up")],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_lastseen),
+    },
+    CommandSpec {
+        name: "marker",
+        description: "Places a stream marker at the current point of the broadcast",
+        args: &[ArgSpec::optional("description", ArgKind::Text, "What happened")],
+        min_role: "moderator",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_marker),
+    },
+    CommandSpec {
+        name: "emotestats",
+        description: "The channel's most used emotes",
+        args: &[ArgSpec::optional("window", ArgKind::Word, "15m, 1h, today or 7d (default: the last hour)")],
+        min_role: "everyone",
+        platforms: TWITCH_ONLY,
+        handler: handler!(handle_emotestats),
+    },
+    CommandSpec {
+        name: "clipit",
+        description: "Clips the live stream",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_clipit),
+    },
+    CommandSpec {
+        name: "lurkers",
+        description: "How many viewers are in chat without talking",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_lurkers),
+    },
+    CommandSpec {
+        name: "watchtime",
+        description: "How long someone has watched while the stream was live",
+        args: &[ArgSpec::optional("user", ArgKind::User, "Who to look up (default: you)")],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_watchtime),
+    },
+    CommandSpec {
+        name: "schedule",
+        description: "The next streams on the Twitch schedule",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_schedule),
+    },
+    CommandSpec {
+        name: "toggle",
+        description: "Flips an avatar toggle",
+        args: &[ArgSpec::required("name", ArgKind::Word, "The chat control")],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_toggle),
+    },
+    CommandSpec {
+        name: "set",
+        description: "Sets an avatar parameter",
+        args: &[
+            ArgSpec::required("name", ArgKind::Word, "The chat control"),
+            ArgSpec::required("value", ArgKind::Word, "The new value"),
+        ],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_set),
+    },
+    CommandSpec {
+        name: "vanish",
+        description: "Clears your messages from Twitch chat",
+        args: &[ArgSpec::optional("announce", ArgKind::Word, "yes to say that you vanished")],
+        min_role: "everyone",
+        platforms: TWITCH_ONLY,
+        handler: handler!(handle_vanish),
+    },
+    CommandSpec {
+        name: "world",
+        description: "The VRChat world the streamer is in",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_world),
+    },
+    CommandSpec {
+        name: "instance",
+        description: "The streamer's VRChat instance",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_instance),
+    },
+    CommandSpec {
+        name: "whoshere",
+        description: "Friends in the streamer's VRChat instance",
+        args: &[],
+        min_role: "everyone",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_whoshere),
+    },
+    CommandSpec {
+        name: "vrchat",
+        description: "Marks the streamer as online or offline in VRChat",
+        args: &[ArgSpec::required("status", ArgKind::Choice(&["online", "offline"]), "online or offline")],
+        min_role: "moderator",
+        platforms: EVERYWHERE,
+        handler: handler!(handle_vrchat_online_offline),
+    },
+];
+
+/// The built-in definition named `name`, if there is one.
+pub fn find_builtin(name: &str) -> Option<&'static CommandSpec> {
+    BUILTIN_COMMANDS.iter().find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
 
-    // Command name not matched by any built-in.
-    Ok(None)
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_fit_discord() {
+        for spec in BUILTIN_COMMANDS {
+            assert_eq!(spec.name, spec.name.to_lowercase());
+            assert!(spec.name.len() <= 32 && !spec.description.is_empty() && spec.description.len() <= 100, "{}", spec.name);
+            let text_args = spec.args.iter().filter(|a| a.kind == ArgKind::Text).count();
+            assert!(text_args == 0 || spec.args.last().unwrap().kind == ArgKind::Text, "{}", spec.name);
+            // Discord wants required options first
+            assert!(spec.args.windows(2).all(|w| w[0].required || !w[1].required), "{}", spec.name);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Built-in `!toggle <name>` and `!set <name> <value>` commands: drive avatar
+//! parameters over OSC through the chat controls in the OSC toggle repository,
+//! each with its own minimum role, value range and cooldown.
+
+use maowbot_common::models::Command;
+use maowbot_common::models::osc_toggle::OscParameterValue;
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::osc_toggle_service::OscChatRefusal;
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+pub async fn handle_toggle(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    _user: &User,
+    args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.osc_toggle_service.clone()) else {
+        return Ok(ctx.text("osc.unavailable", &[]));
+    };
+    let Some(name) = args.get("name") else {
+        return Ok(ctx.text("osc.toggle_usage", &[]));
+    };
+    let name = name.to_lowercase();
+    Ok(reply(ctx, &name, service.chat_toggle(&name, ctx.user_roles).await))
+}
+
+pub async fn handle_set(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    _user: &User,
+    args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.osc_toggle_service.clone()) else {
+        return Ok(ctx.text("osc.unavailable", &[]));
+    };
+    let (Some(name), Some(value)) = (args.get("name"), args.get("value")) else {
+        return Ok(ctx.text("osc.set_usage", &[]));
+    };
+    let name = name.to_lowercase();
+    Ok(reply(ctx, &name, service.chat_set(&name, value, ctx.user_roles).await))
+}
+
+fn reply(ctx: &CommandContext<'_>, name: &str, result: Result<OscParameterValue, OscChatRefusal>) -> String {
+    match result {
+        Ok(value) => ctx.text("osc.set", &[("name", name), ("value", &value.to_string())]),
+        Err(OscChatRefusal::Unknown) => ctx.text("osc.unknown", &[("name", name)]),
+        Err(OscChatRefusal::NotAllowed { role }) => ctx.text("osc.not_allowed", &[("name", name), ("role", &role)]),
+        Err(OscChatRefusal::Cooldown { seconds }) => ctx.text("osc.cooldown", &[("name", name), ("seconds", &seconds.to_string())]),
+        Err(OscChatRefusal::NotToggle) => ctx.text("osc.not_toggle", &[("name", name)]),
+        Err(OscChatRefusal::BadValue(error)) => ctx.text("osc.bad_value", &[("name", name), ("error", &error)]),
+        Err(OscChatRefusal::Failed(error)) => ctx.text("osc.failed", &[("error", &error.to_string())]),
+    }
+}
@@ -1,13 +1,14 @@
 use maowbot_common::models::Command;
 use maowbot_common::models::user::User;
 use crate::Error;
+use crate::services::command_spec::CommandArgs;
 use crate::services::twitch::command_service::CommandContext;
 
 pub async fn handle_ping(
     _cmd: &Command,
-    _ctx: &CommandContext<'_>,
+    ctx: &CommandContext<'_>,
     _user: &User,
-    _raw_args: &str,
+    _args: &CommandArgs,
 ) -> Result<String, Error> {
-    Ok("pong".to_string())
+    Ok(ctx.text("ping.reply", &[]))
 }
\ No newline at end of file
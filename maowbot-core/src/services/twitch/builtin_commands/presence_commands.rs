@@ -0,0 +1,65 @@
+//! Built-in `!lurkers` and `!watchtime [user]` commands, answered from the
+//! chatter presence service: how many viewers are in chat without talking,
+//! and how long someone has been in chat while the stream was live.
+
+use maowbot_common::models::Command;
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+/// e.g. "3d 4h", "2h 15m", "12m".
+fn format_watchtime(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+pub async fn handle_lurkers(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    _user: &User,
+    _args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(count) = ctx.plugin_manager.as_ref()
+        .and_then(|pm| pm.chatter_presence_service.clone())
+        .and_then(|service| service.lurkers())
+    else {
+        return Ok(ctx.text("presence.unavailable", &[]));
+    };
+    Ok(ctx.text("presence.lurkers", &[
+        ("count", &count.lurking.to_string()),
+        ("present", &count.present.to_string()),
+    ]))
+}
+
+pub async fn handle_watchtime(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    user: &User,
+    args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.chatter_presence_service.clone()) else {
+        return Ok(ctx.text("presence.unavailable", &[]));
+    };
+    let target = match args.get("user") {
+        Some(name) => name.to_string(),
+        None => match user.global_username.clone() {
+            Some(name) => name,
+            None => return Ok(ctx.text("presence.watchtime_usage", &[])),
+        },
+    };
+    match service.watchtime(&target).await? {
+        Some(watched) if watched.watch_seconds >= 60 => Ok(ctx.text("presence.watchtime", &[
+            ("user", &watched.display_name),
+            ("time", &format_watchtime(watched.watch_seconds)),
+        ])),
+        _ => Ok(ctx.text("presence.no_watchtime", &[("user", &target)])),
+    }
+}
@@ -0,0 +1,40 @@
+//! Built-in `!schedule` command: the next few segments of the broadcaster's
+//! Twitch stream schedule, from the schedule service's cache.
+
+use maowbot_common::models::Command;
+use maowbot_common::models::user::User;
+use crate::Error;
+use crate::services::command_spec::CommandArgs;
+use crate::services::twitch::command_service::CommandContext;
+
+/// Segments listed in one reply, to stay well under Twitch's 500 characters.
+const SHOWN_SEGMENTS: usize = 3;
+
+pub async fn handle_schedule(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    _user: &User,
+    _args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.schedule_service.clone()) else {
+        return Ok(ctx.text("schedule.unavailable", &[]));
+    };
+    let segments = service.upcoming().await?;
+    let entries: Vec<String> = segments.iter()
+        .filter(|s| s.canceled_until.is_none())
+        .take(SHOWN_SEGMENTS)
+        .map(|s| {
+            let when = s.start_time.format("%a %b %-d, %H:%M UTC");
+            match &s.category {
+                Some(category) if !s.title.is_empty() => format!("{} – {} ({})", when, s.title, category.name),
+                Some(category) => format!("{} – {}", when, category.name),
+                None if !s.title.is_empty() => format!("{} – {}", when, s.title),
+                None => when.to_string(),
+            }
+        })
+        .collect();
+    if entries.is_empty() {
+        return Ok(ctx.text("schedule.empty", &[]));
+    }
+    Ok(ctx.text("schedule.upcoming", &[("segments", &entries.join(" | "))]))
+}
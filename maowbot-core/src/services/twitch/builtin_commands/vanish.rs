@@ -5,10 +5,10 @@
 //! • If a truthy flag is present, sends a “🪄 … has vanished!” confirmation.
 
 use crate::Error;
+use crate::services::command_spec::CommandArgs;
 use crate::services::twitch::command_service::CommandContext;
 use maowbot_common::models::{Command, user::User};
 use maowbot_common::models::platform::Platform::TwitchIRC;
-use maowbot_common::traits::repository_traits::CredentialsRepository;
 use maowbot_common::traits::api::TwitchApi;
 
 /// Returns true for “yes, y, true, 1”
@@ -20,10 +20,10 @@ pub async fn handle_vanish(
     _cmd: &Command,
     ctx: &CommandContext<'_>,
     user: &User,
-    raw_args: &str,
+    args: &CommandArgs,
 ) -> Result<String, Error> {
     // 1) Resolve target login
-    let mut login = user.global_username.clone().unwrap_or_else(String::new);
+    let mut login = user.global_username.clone().unwrap_or_default();
     if login.is_empty() {
         if let Ok(Some(cred)) = ctx
             .credentials_repo
@@ -49,8 +49,8 @@ pub async fn handle_vanish(
     }
 
     // 3) Optionally confirm
-    if is_truthy(raw_args) {
-        Ok(format!("🪄 {} has vanished!", login))
+    if args.get("announce").is_some_and(is_truthy) {
+        Ok(ctx.text("vanish.done", &[("user", &login)]))
     } else {
         Ok(String::new())
     }
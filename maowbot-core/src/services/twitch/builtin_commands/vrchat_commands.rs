@@ -1,5 +1,8 @@
 use crate::Error;
-use crate::platforms::vrchat::client::VRChatClient;
+use crate::platforms::vrchat::client::{is_session_expired, VRChatClient};
+use crate::platforms::vrchat::location::InstanceLocation;
+use crate::platforms::vrchat::world_cache::{world_cache, CacheTtls};
+use crate::services::command_spec::CommandArgs;
 use crate::services::twitch::command_service::CommandContext;
 use tracing::{info, warn};
 use maowbot_common::models::Command;
@@ -18,16 +21,17 @@ fn short_ymd(date_str: &str) -> String {
 /// handle_world is invoked for the `!world` command.
 ///
 /// It retrieves VRChat world info, then outputs:
-/// - one message with name, author, capacity, release status, published date, and last updated (YYYY-MM-DD).
+/// - one message with name, author, capacity, release status, published date, last updated (YYYY-MM-DD)
+///   and supported platforms.
 /// - one or more messages for the description if present, chunking if necessary.
 pub async fn handle_world(
     _cmd: &Command,
     ctx: &CommandContext<'_>,
     _user: &User,
-    _raw_args: &str,
+    _args: &CommandArgs,
 ) -> Result<String, Error> {
-    // 1) Determine which VRChat account to use from bot_config
-    let configured_account = match ctx.bot_config_repo.get_value("vrchat_active_account").await? {
+    // 1) Determine which VRChat account to use from the settings
+    let configured_account = match ctx.settings.get("vrchat_active_account") {
         Some(val) if !val.trim().is_empty() => val,
         _ => "broadcaster".to_string(),
     };
@@ -42,46 +46,50 @@ pub async fn handle_world(
     let cred = match vrc_cred_opt {
         Some(c) => c,
         None => {
-            return Ok(format!(
-                "No VRChat credentials found for account '{}'. \
-Please set 'vrchat_active_account' or run 'account add vrchat'.",
-                configured_account
-            ));
+            return Ok(ctx.text("vrchat.no_account", &[("account", &configured_account)]));
         }
     };
 
-    // 3) Fetch the current world info
+    // 3) Fetch the current world info (cached, see world_cache)
     let client = VRChatClient::new(&cred.primary_token)?;
-    let winfo_opt = client.fetch_current_world_api().await?;
-    if winfo_opt.is_none() {
-        return Ok("User is offline or not in any world.".to_string());
-    }
-    let w = winfo_opt.unwrap();
+    let ttls = CacheTtls::from_settings(ctx.settings);
+    let inst_opt = match world_cache().location(&client, &cred.user_name, ttls.location).await {
+        Err(e) if is_session_expired(&e) => {
+            warn!("handle_world => {}", e);
+            return Ok(ctx.text("vrchat.session_expired", &[("account", &configured_account)]));
+        }
+        other => other?,
+    };
+    let Some(world_id) = inst_opt.and_then(|i| i.world_id).filter(|w| !w.is_empty()) else {
+        return Ok(ctx.text("vrchat.not_in_world", &[]));
+    };
+    let w = world_cache().world(&client, &world_id, ttls.world).await?;
 
     // 4) Convert published/updated fields to short YYYY-MM-DD if possible
     let published_str = w
         .published_at
         .as_deref()
         .map(short_ymd)
-        .unwrap_or_else(|| "(unknown)".to_string());
+        .unwrap_or_else(|| ctx.text("vrchat.unknown_date", &[]));
     let updated_str = w
         .updated_at
         .as_deref()
         .map(short_ymd)
-        .unwrap_or_else(|| "(unknown)".to_string());
+        .unwrap_or_else(|| ctx.text("vrchat.unknown_date", &[]));
 
     // 5) Prepare the first message
     let release_str = w.release_status.clone().unwrap_or_default();
-    let first_message = format!(
-        "[World Info] Name: {} | Author: {} | Capacity: {} | Status: {} | \
-Published: {} | Last Updated: {}",
-        w.name.trim(),
-        w.author_name.trim(),
-        w.capacity,
-        release_str.trim(),
-        published_str,   // already short-ymd
-        updated_str      // already short-ymd
-    );
+    let platforms = w.platform_summary();
+    let platforms = if platforms.is_empty() { ctx.text("vrchat.unknown_date", &[]) } else { platforms };
+    let first_message = ctx.text("vrchat.world_info", &[
+        ("name", w.name.trim()),
+        ("author", w.author_name.trim()),
+        ("capacity", &w.capacity.to_string()),
+        ("status", release_str.trim()),
+        ("published", &published_str),   // already short-ymd
+        ("updated", &updated_str),       // already short-ymd
+        ("platforms", &platforms),
+    ]);
 
     // 6) Next, handle the description (in separate messages, chunked if too long)
     let mut results = vec![first_message];
@@ -105,13 +113,12 @@ Published: {} | Last Updated: {}",
                 };
                 
                 let chunk_text = &remaining[..chunk_size];
-                let prefix = if first_chunk {
+                if first_chunk {
                     first_chunk = false;
-                    "Description: "
+                    results.push(ctx.text("vrchat.world_description", &[("text", chunk_text)]));
                 } else {
-                    ""
-                };
-                results.push(format!("{}{}", prefix, chunk_text));
+                    results.push(chunk_text.to_string());
+                }
                 remaining = &remaining[chunk_size..];
             }
         }
@@ -124,14 +131,15 @@ Published: {} | Last Updated: {}",
 ///
 /// We retrieve the user’s current instance. If it’s joinable, produce a
 /// `vrchat.com/home/launch` link. Otherwise produce a `.../world/<worldId>/info` link.
+/// The access type (public, friends+, ...) and region are shown alongside.
 pub async fn handle_instance(
     _cmd: &Command,
     ctx: &CommandContext<'_>,
     _user: &User,
-    _raw_args: &str,
+    _args: &CommandArgs,
 ) -> Result<String, Error> {
     // 1) Determine which VRChat account to use
-    let configured_account = match ctx.bot_config_repo.get_value("vrchat_active_account").await? {
+    let configured_account = match ctx.settings.get("vrchat_active_account") {
         Some(val) if !val.trim().is_empty() => val,
         _ => "broadcaster".to_string(),
     };
@@ -146,69 +154,94 @@ pub async fn handle_instance(
     let cred = match vrc_cred_opt {
         Some(c) => c,
         None => {
-            return Ok(format!(
-                "No VRChat credentials found for account '{}'. \
-Please set 'vrchat_active_account' or run 'account add vrchat'.",
-                configured_account
-            ));
+            return Ok(ctx.text("vrchat.no_account", &[("account", &configured_account)]));
         }
     };
 
-    // 3) Fetch instance
+    // 3) Fetch instance (cached briefly, see world_cache)
     let client = VRChatClient::new(&cred.primary_token)?;
-    let inst_opt = client.fetch_current_instance_api().await?;
+    let ttls = CacheTtls::from_settings(ctx.settings);
+    let inst_opt = match world_cache().location(&client, &cred.user_name, ttls.location).await {
+        Err(e) if is_session_expired(&e) => {
+            warn!("handle_instance => {}", e);
+            return Ok(ctx.text("vrchat.session_expired", &[("account", &configured_account)]));
+        }
+        other => other?,
+    };
     let inst = match inst_opt {
         Some(i) => i,
-        None => return Ok("User is offline or no instance found.".into()),
+        None => return Ok(ctx.text("vrchat.no_instance", &[])),
     };
 
     // 4) Retrieve the world name from world_id
     let world_id = inst.world_id.clone().unwrap_or_default();
     if world_id.is_empty() {
-        return Ok("Currently in an unknown/hidden world.".to_string());
+        return Ok(ctx.text("vrchat.hidden_world", &[]));
     }
-    let winfo = client.fetch_world_info(&world_id).await?;
+    let winfo = world_cache().world(&client, &world_id, ttls.world).await?;
     let world_name = winfo.name;
 
-    // 5) Decide if instance is joinable
-    //    e.g. if location doesn’t have "private"/"invite", treat it as joinable
-    let location = inst.location.unwrap_or_default().to_lowercase();
+    // 5) Parse the instance: who may join decides between a launch link and the world page
     let instance_id = inst.instance_id.unwrap_or_default();
-    if instance_id.is_empty() {
-        return Ok(format!("Currently in world '{}', unknown instance.", world_name));
-    }
-    let is_joinable = !(location.contains("private") || location.contains("invite"));
-
-    // 6) Construct link
-    let link = if is_joinable {
-        format!(
-            "https://vrchat.com/home/launch?worldId={}&instanceId={}",
-            world_id, instance_id
-        )
-    } else {
-        format!("https://vrchat.com/home/world/{}/info", world_id)
+    let Some(location) = InstanceLocation::from_parts(&world_id, &instance_id) else {
+        return Ok(ctx.text("vrchat.unknown_instance", &[("world", &world_name)]));
     };
 
-    Ok(format!(
-        "[world] '{}' - link: {}",
-        world_name, link
-    ))
+    let access = match &location.region {
+        Some(region) => format!("{}, {}", location.access, region.to_uppercase()),
+        None => location.access.to_string(),
+    };
+    Ok(ctx.text("vrchat.instance", &[
+        ("world", &world_name),
+        ("link", &location.invite_link()),
+        ("access", &access),
+    ]))
+}
+
+/// handle_whoshere is invoked for the `!whosHere` command: the streamer's
+/// friends in their current instance, as of the presence service's last poll.
+/// VRChat doesn't tell us about people who aren't friends.
+pub async fn handle_whoshere(
+    _cmd: &Command,
+    ctx: &CommandContext<'_>,
+    _user: &User,
+    _args: &CommandArgs,
+) -> Result<String, Error> {
+    let Some(service) = ctx.plugin_manager.as_ref().and_then(|pm| pm.vrchat_presence_service.clone()) else {
+        return Ok(ctx.text("vrchat.whoshere_unavailable", &[]));
+    };
+    let snapshot = service.snapshot();
+    let Some(location) = snapshot.location else {
+        return Ok(ctx.text("vrchat.no_instance", &[]));
+    };
+    let world = snapshot.world_name.unwrap_or(location);
+    if snapshot.friends.is_empty() {
+        return Ok(ctx.text("vrchat.whoshere_nobody", &[("world", &world)]));
+    }
+    let names = snapshot.friends.iter()
+        .map(|f| f.display_name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(ctx.text("vrchat.whoshere", &[
+        ("world", &world),
+        ("count", &snapshot.friends.len().to_string()),
+        ("names", &names),
+    ]))
 }
 
 /// handle_vrchat_online_offline might handle sub-commands if needed (example).
 pub async fn handle_vrchat_online_offline(
     _cmd: &Command,
-    _ctx: &CommandContext<'_>,
+    ctx: &CommandContext<'_>,
     _user: &User,
-    raw_args: &str,
+    args: &CommandArgs,
 ) -> Result<String, Error> {
-    let arg = raw_args.trim().to_lowercase();
-    match arg.as_str() {
-        "offline" => Ok("VRChat commands are now forced offline. (Stub)".to_string()),
-        "online" => Ok("VRChat commands now assume online. (Stub)".to_string()),
+    match args.get("status").unwrap_or_default() {
+        "offline" => Ok(ctx.text("vrchat.forced_offline", &[])),
+        "online" => Ok(ctx.text("vrchat.assume_online", &[])),
         _ => {
-            warn!("!vrchat unknown argument => '{}'", raw_args);
-            Ok("Usage: !vrchat <offline|online>".to_string())
+            warn!("!vrchat unknown argument => '{}'", args.raw());
+            Ok(ctx.text("vrchat.usage", &[]))
         }
     }
 }
\ No newline at end of file
@@ -2,15 +2,15 @@ use tracing::{info, warn, error};
 use serde_json::json;
 use uuid::Uuid;
 use maowbot_common::models::user::User;
-use maowbot_ai::plugins::ai_service::AiService;
-use maowbot_common::traits::api::AiApi;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::timeout;
 use crate::Error;
 use crate::services::twitch::redeem_service::RedeemHandlerContext;
 use crate::platforms::twitch::requests::channel_points::Redemption;
-use crate::services::message_sender::{MessageSender, MessageResponse, push_pending_sources};
+use crate::services::message_sender::{MessageSender, push_pending_sources};
+use crate::services::ai_safety::ShapedResponse;
+use maowbot_proto::plugs::{plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse};
 
 // Helper function to generate an AI text response
 async fn generate_ai_response(
@@ -178,6 +178,33 @@ async fn get_user_from_twitch_id(
     }
 }
 
+/// Hands the filtered answer to plugins as an `ai_response` GameEvent, so TTS
+/// overlays read the same text chat got.
+async fn announce_ai_response(
+    ctx: &RedeemHandlerContext<'_>,
+    channel: &str,
+    redeem: &str,
+    shaped: &ShapedResponse,
+) {
+    let Some(plugin_manager) = ctx.redeem_service.platform_manager.plugin_manager() else { return };
+    let payload = json!({
+        "channel": channel,
+        "redeem": redeem,
+        "chunks": shaped.chunks,
+        "tts": shaped.tts,
+        "withheld": shaped.withheld,
+    });
+    plugin_manager.broadcast(
+        PluginStreamResponse {
+            payload: Some(RespPayload::GameEvent(GameEvent {
+                name: "ai_response".to_string(),
+                json: payload.to_string(),
+            })),
+        },
+        None,
+    ).await;
+}
+
 /// Handles the standard AI redeem that performs a serious AI response
 pub async fn handle_askai_redeem(
     ctx: &RedeemHandlerContext<'_>,
@@ -206,7 +233,7 @@ pub async fn handle_askai_redeem(
             client.update_redemption_status(
                 broadcaster_id, 
                 reward_id, 
-                &[&redemption_id],
+                &[redemption_id],
                 "CANCELED"
             ).await
         } else if let Some(client) = helix_client_opt {
@@ -214,7 +241,7 @@ pub async fn handle_askai_redeem(
             client.update_redemption_status(
                 broadcaster_id, 
                 reward_id, 
-                &[&redemption_id],
+                &[redemption_id],
                 "CANCELED"
             ).await
         } else {
@@ -314,29 +341,32 @@ pub async fn handle_askai_redeem(
         });
         
         // Send the response using the enhanced AI response sender
-        if let Err(e) = message_sender.send_ai_response_to_twitch(
+        match message_sender.send_ai_response_to_twitch(
             broadcaster_login,
             &response,
             Some(&empty_response),
             ctx.active_credential.as_ref().map(|cred| cred.credential_id),
             user.user_id
         ).await {
-            error!("Failed to send AI response to chat: {:?}", e);
+            Ok(shaped) => announce_ai_response(ctx, broadcaster_login, "askai", &shaped).await,
+            Err(e) => {
+                error!("Failed to send AI response to chat: {:?}", e);
             
-            // Cancel the redemption since we couldn't send the response
-            let helix_client_opt = ctx.redeem_service.platform_manager.get_twitch_client().await;
-            if let Some(client) = ctx.helix_client.as_ref().or(helix_client_opt.as_ref()) {
-                let _ = client
-                    .update_redemption_status(
-                        &redemption.broadcaster_id,
-                        &redemption.reward.id,
-                        &[&redemption.id],
-                        "CANCELED",
-                    )
-                    .await;
-            }
+                // Cancel the redemption since we couldn't send the response
+                let helix_client_opt = ctx.redeem_service.platform_manager.get_twitch_client().await;
+                if let Some(client) = ctx.helix_client.as_ref().or(helix_client_opt.as_ref()) {
+                    let _ = client
+                        .update_redemption_status(
+                            &redemption.broadcaster_id,
+                            &redemption.reward.id,
+                            &[&redemption.id],
+                            "CANCELED",
+                        )
+                        .await;
+                }
             
-            return Err(Error::Internal(format!("Failed to send message: {}", e)));
+                return Err(Error::Internal(format!("Failed to send message: {}", e)));
+            }
         }
     } else {
         error!("No broadcaster login found in redemption - can't send response");
@@ -370,7 +400,7 @@ pub async fn handle_askai_redeem(
             .update_redemption_status(
                 broadcaster_id,
                 reward_id,
-                &[&redemption_id],
+                &[redemption_id],
                 "FULFILLED",
             )
             .await
@@ -383,7 +413,7 @@ pub async fn handle_askai_redeem(
                     .update_redemption_status(
                         broadcaster_id,
                         reward_id,
-                        &[&redemption_id],
+                        &[redemption_id],
                         "FULFILLED",
                     )
                     .await;
@@ -395,7 +425,7 @@ pub async fn handle_askai_redeem(
             .update_redemption_status(
                 broadcaster_id,
                 reward_id,
-                &[&redemption_id],
+                &[redemption_id],
                 "FULFILLED",
             )
             .await;
@@ -433,7 +463,7 @@ pub async fn handle_askmao_redeem(
                 .update_redemption_status(
                     broadcaster_id,
                     reward_id,
-                    &[&redemption_id],
+                    &[redemption_id],
                     "CANCELED",
                 )
                 .await?;
@@ -494,7 +524,7 @@ pub async fn handle_askmao_redeem(
         Ok(resp) => resp,
         Err(e) => {
             error!("Error generating cat-like AI response: {:?}", e);
-            format!("Meow? *looks confused* Something went wrong with my cat brain. Try again later!")
+            "Meow? *looks confused* Something went wrong with my cat brain. Try again later!".to_string()
         }
     };
     
@@ -513,28 +543,31 @@ pub async fn handle_askmao_redeem(
         });
         
         // Send the response using the enhanced AI response sender
-        if let Err(e) = message_sender.send_ai_response_to_twitch(
+        match message_sender.send_ai_response_to_twitch(
             broadcaster_login,
             &response,
             Some(&empty_response),
             ctx.active_credential.as_ref().map(|cred| cred.credential_id),
             user.user_id
         ).await {
-            error!("Failed to send askmaow response to chat: {:?}", e);
+            Ok(shaped) => announce_ai_response(ctx, broadcaster_login, "askmao", &shaped).await,
+            Err(e) => {
+                error!("Failed to send askmaow response to chat: {:?}", e);
             
-            // Cancel the redemption since we couldn't send the response
-            if let Some(client) = &ctx.helix_client {
-                let _ = client
-                    .update_redemption_status(
-                        &redemption.broadcaster_id,
-                        &redemption.reward.id,
-                        &[&redemption.id],
-                        "CANCELED",
-                    )
-                    .await;
-            }
+                // Cancel the redemption since we couldn't send the response
+                if let Some(client) = &ctx.helix_client {
+                    let _ = client
+                        .update_redemption_status(
+                            &redemption.broadcaster_id,
+                            &redemption.reward.id,
+                            &[&redemption.id],
+                            "CANCELED",
+                        )
+                        .await;
+                }
             
-            return Err(Error::Internal(format!("Failed to send message: {}", e)));
+                return Err(Error::Internal(format!("Failed to send message: {}", e)));
+            }
         }
     } else {
         error!("No broadcaster login found in redemption");
@@ -567,7 +600,7 @@ pub async fn handle_askmao_redeem(
             .update_redemption_status(
                 broadcaster_id,
                 reward_id,
-                &[&redemption_id],
+                &[redemption_id],
                 "FULFILLED",
             )
             .await?;
@@ -691,7 +724,7 @@ audience.";
             ctx.redeem_service.credentials_repo.clone(),
             ctx.redeem_service.platform_manager.clone(),
         );
-        if let Err(e) = message_sender
+        match message_sender
             .send_ai_response_to_twitch(
                 broadcaster_login,
                 &response,
@@ -701,21 +734,24 @@ audience.";
             )
             .await
         {
-            error!("Failed to send AI search response to chat: {:?}", e);
+            Ok(shaped) => announce_ai_response(ctx, broadcaster_login, "askai_search", &shaped).await,
+            Err(e) => {
+                error!("Failed to send AI search response to chat: {:?}", e);
             
-            // Cancel the redemption since we couldn't send the response
-            if let Some(client) = &ctx.helix_client {
-                let _ = client
-                    .update_redemption_status(
-                        &redemption.broadcaster_id,
-                        &redemption.reward.id,
-                        &[&redemption.id],
-                        "CANCELED",
-                    )
-                    .await;
-            }
+                // Cancel the redemption since we couldn't send the response
+                if let Some(client) = &ctx.helix_client {
+                    let _ = client
+                        .update_redemption_status(
+                            &redemption.broadcaster_id,
+                            &redemption.reward.id,
+                            &[&redemption.id],
+                            "CANCELED",
+                        )
+                        .await;
+                }
             
-            return Err(Error::Internal(format!("Failed to send message: {}", e)));
+                return Err(Error::Internal(format!("Failed to send message: {}", e)));
+            }
         }
     } else {
         error!("No broadcaster login found in redemption");
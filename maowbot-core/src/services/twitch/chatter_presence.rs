@@ -0,0 +1,355 @@
+// File: maowbot-core/src/services/twitch/chatter_presence.rs
+//
+// Who is in the broadcaster's Twitch chat. Twitch no longer sends IRC
+// JOIN/PART reliably, so every `twitch.chatters.poll_seconds` the Helix
+// chatters list is fetched and compared with the last one; arrivals and
+// departures are published together as `BotEvent::TwitchChatters`. While the
+// stream is live, everyone found by two polls in a row is credited the time
+// between them as watchtime. Viewers who haven't chatted for
+// `twitch.chatters.lurk_minutes` count as lurkers for `!lurkers`.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::models::watchtime::ViewerWatchtime;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, WatchtimeRepository};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::manager::PlatformManager;
+use crate::platforms::twitch::requests::chatters::Chatter;
+use crate::platforms::twitch::routing::TwitchOperation;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// Polls missed beyond this many intervals aren't credited as watched.
+const MAX_CREDITED_POLLS: i64 = 2;
+
+/// A viewer in the broadcaster's chat.
+#[derive(Debug, Clone)]
+pub struct PresentViewer {
+    pub user_id: String,
+    pub login: String,
+    pub display_name: String,
+    /// When we first saw them here (the poll that found them)
+    pub since: DateTime<Utc>,
+}
+
+impl PresentViewer {
+    fn new(chatter: Chatter, since: DateTime<Utc>) -> Self {
+        Self {
+            user_id: chatter.user_id,
+            login: chatter.user_login,
+            display_name: chatter.user_name,
+            since,
+        }
+    }
+
+    fn to_chatter(&self) -> Chatter {
+        Chatter {
+            user_id: self.user_id.clone(),
+            user_login: self.login.clone(),
+            user_name: self.display_name.clone(),
+        }
+    }
+
+    /// Here for at least `after` without chatting; `last_chat` may be from
+    /// before the poll that found them.
+    pub fn is_lurking(&self, last_chat: Option<DateTime<Utc>>, now: DateTime<Utc>, after: Duration) -> bool {
+        now - last_chat.unwrap_or(self.since) >= after
+    }
+}
+
+/// How many of the viewers in chat are lurking, as of the last poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LurkerCount {
+    pub present: usize,
+    pub lurking: usize,
+}
+
+/// Viewers who arrived in and left chat between two polls.
+pub fn diff_chatters(
+    previous: &HashMap<String, PresentViewer>,
+    current: &[Chatter],
+) -> (Vec<Chatter>, Vec<Chatter>) {
+    let here: HashSet<&str> = current.iter().map(|c| c.user_id.as_str()).collect();
+    let joined = current.iter()
+        .filter(|c| !previous.contains_key(&c.user_id))
+        .cloned()
+        .collect();
+    let mut left: Vec<Chatter> = previous.values()
+        .filter(|v| !here.contains(v.user_id.as_str()))
+        .map(PresentViewer::to_chatter)
+        .collect();
+    left.sort_by(|a, b| a.user_login.cmp(&b.user_login));
+    (joined, left)
+}
+
+#[derive(Default)]
+struct PresenceState {
+    /// The broadcaster's login; None until the first poll
+    channel: Option<String>,
+    /// By Twitch user id
+    viewers: HashMap<String, PresentViewer>,
+    /// Last chat line per Twitch user id, kept for viewers not in the list yet
+    last_chat: HashMap<String, DateTime<Utc>>,
+    live: bool,
+    checked_at: Option<DateTime<Utc>>,
+}
+
+pub struct ChatterPresenceService {
+    repo: Arc<dyn WatchtimeRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    state: Mutex<PresenceState>,
+}
+
+impl ChatterPresenceService {
+    pub fn new(
+        repo: Arc<dyn WatchtimeRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            repo,
+            credentials_repo,
+            platform_manager,
+            event_bus,
+            settings,
+            state: Mutex::new(PresenceState::default()),
+        }
+    }
+
+    fn poll_seconds(&self) -> u64 {
+        self.settings.get_u64("twitch.chatters.poll_seconds").unwrap_or(60).max(30)
+    }
+
+    fn lurk_after(&self) -> Duration {
+        Duration::minutes(self.settings.get_u64("twitch.chatters.lurk_minutes").unwrap_or(10).max(1) as i64)
+    }
+
+    /// Polls every `twitch.chatters.poll_seconds` while `twitch.chatters.enabled`,
+    /// and notes when each viewer last chatted.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                if service.settings.get_bool("twitch.chatters.enabled").unwrap_or(true) {
+                    if let Err(e) = service.poll().await {
+                        warn!("[Chatters] poll failed: {:?}", e);
+                    }
+                } else {
+                    *service.state.lock() = PresenceState::default();
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(StdDuration::from_secs(service.poll_seconds())) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Chatters] poll loop stopped");
+        });
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event),
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Chatters] event loop stopped");
+        });
+    }
+
+    fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, timestamp, metadata, .. } = event else { return };
+        if platform != "twitch-irc" {
+            return;
+        }
+        let Some(user_id) = metadata.get("platform_user_id").and_then(|v| v.as_str()).filter(|id| !id.is_empty()) else {
+            return;
+        };
+        let channel = channel.trim_start_matches('#').to_lowercase();
+        let mut state = self.state.lock();
+        if state.channel.as_deref() == Some(channel.as_str()) {
+            state.last_chat.insert(user_id.to_string(), timestamp);
+        }
+    }
+
+    /// Fetches the chatters list, announces who came and went, and credits
+    /// watchtime while live.
+    pub async fn poll(&self) -> Result<(), Error> {
+        if self.credentials_repo.get_broadcaster_credential(&Platform::Twitch).await?.is_none() {
+            *self.state.lock() = PresenceState::default();
+            return Ok(());
+        }
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        let helix = self.platform_manager.twitch_accounts()?.helix_for(TwitchOperation::Moderation).await?;
+        // The bot and the broadcaster are always "in chat"; they aren't viewers
+        let own = [broadcaster.broadcaster_id.as_str(), helix.acting_id.as_str()];
+        let chatters: Vec<Chatter> = helix.client
+            .get_chatters(&broadcaster.broadcaster_id, &helix.acting_id)
+            .await?
+            .into_iter()
+            .filter(|c| !own.contains(&c.user_id.as_str()))
+            .collect();
+        let live = broadcaster.client.fetch_live_stream(&broadcaster.broadcaster_id).await?.is_some();
+        let now = Utc::now();
+        let channel = broadcaster.login;
+
+        let (joined, left, credited) = {
+            let mut state = self.state.lock();
+            if state.channel.as_deref() != Some(channel.as_str()) {
+                // First poll (or a different broadcaster): whoever is here is
+                // the baseline, not a wave of joins
+                info!("[Chatters] tracking {} viewer(s) in {}", chatters.len(), channel);
+                *state = PresenceState {
+                    channel: Some(channel),
+                    viewers: chatters.into_iter()
+                        .map(|c| (c.user_id.clone(), PresentViewer::new(c, now)))
+                        .collect(),
+                    last_chat: HashMap::new(),
+                    live,
+                    checked_at: Some(now),
+                };
+                return Ok(());
+            }
+
+            let (joined, left) = diff_chatters(&state.viewers, &chatters);
+            let credited = match state.checked_at {
+                Some(previous) if live && state.live => {
+                    let max = (self.poll_seconds() as i64) * MAX_CREDITED_POLLS;
+                    let seconds = (now - previous).num_seconds().clamp(0, max);
+                    state.viewers.values()
+                        .filter(|v| !left.iter().any(|l| l.user_id == v.user_id))
+                        .map(|v| ViewerWatchtime {
+                            channel: channel.clone(),
+                            twitch_user_id: v.user_id.clone(),
+                            user_login: v.login.clone(),
+                            display_name: v.display_name.clone(),
+                            watch_seconds: seconds,
+                            first_seen: v.since,
+                            last_seen: now,
+                        })
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+
+            for viewer in &left {
+                state.viewers.remove(&viewer.user_id);
+            }
+            for chatter in &joined {
+                state.viewers.insert(chatter.user_id.clone(), PresentViewer::new(chatter.clone(), now));
+            }
+            let lurk_after = self.lurk_after();
+            let PresenceState { viewers, last_chat, .. } = &mut *state;
+            last_chat.retain(|id, at| viewers.contains_key(id) || now - *at < lurk_after);
+            state.live = live;
+            state.checked_at = Some(now);
+            (joined, left, credited)
+        };
+
+        if !credited.is_empty() {
+            self.repo.add_watchtime(&credited).await?;
+        }
+        if !joined.is_empty() || !left.is_empty() {
+            debug!("[Chatters] {} joined, {} left {}", joined.len(), left.len(), channel);
+            let present = self.state.lock().viewers.len();
+            self.event_bus.publish(BotEvent::TwitchChatters {
+                channel,
+                joined,
+                left,
+                present,
+                timestamp: now,
+            }).await;
+        }
+        Ok(())
+    }
+
+    /// Viewers in chat as of the last poll.
+    pub fn viewers(&self) -> Vec<PresentViewer> {
+        self.state.lock().viewers.values().cloned().collect()
+    }
+
+    /// None until the first poll has found the broadcaster's chat.
+    pub fn lurkers(&self) -> Option<LurkerCount> {
+        let state = self.state.lock();
+        state.channel.as_ref()?;
+        let now = Utc::now();
+        let lurk_after = self.lurk_after();
+        let lurking = state.viewers.values()
+            .filter(|v| v.is_lurking(state.last_chat.get(&v.user_id).copied(), now, lurk_after))
+            .count();
+        Some(LurkerCount { present: state.viewers.len(), lurking })
+    }
+
+    /// `login`'s watchtime in the broadcaster's channel.
+    pub async fn watchtime(&self, login: &str) -> Result<Option<ViewerWatchtime>, Error> {
+        let Some(channel) = self.state.lock().channel.clone() else {
+            return Ok(None);
+        };
+        self.repo.get_watchtime(&channel, login.trim_start_matches('@')).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chatter(id: &str, login: &str) -> Chatter {
+        Chatter {
+            user_id: id.to_string(),
+            user_login: login.to_string(),
+            user_name: login.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_finds_joins_and_leaves() {
+        let now = Utc::now();
+        let previous: HashMap<String, PresentViewer> = [chatter("1", "mochi"), chatter("2", "tofu")]
+            .into_iter()
+            .map(|c| (c.user_id.clone(), PresentViewer::new(c, now)))
+            .collect();
+        let (joined, left) = diff_chatters(&previous, &[chatter("1", "mochi"), chatter("3", "miso")]);
+        assert_eq!(joined, vec![chatter("3", "miso")]);
+        assert_eq!(left, vec![chatter("2", "tofu")]);
+    }
+
+    #[test]
+    fn test_lurking_counts_from_the_last_chat_or_arrival() {
+        let now = Utc::now();
+        let viewer = PresentViewer::new(chatter("1", "mochi"), now - Duration::minutes(30));
+        let after = Duration::minutes(10);
+        assert!(viewer.is_lurking(None, now, after));
+        assert!(viewer.is_lurking(Some(now - Duration::minutes(45)), now, after));
+        assert!(!viewer.is_lurking(Some(now - Duration::minutes(2)), now, after));
+
+        let newcomer = PresentViewer::new(chatter("2", "tofu"), now - Duration::minutes(3));
+        assert!(!newcomer.is_lurking(None, now, after));
+    }
+}
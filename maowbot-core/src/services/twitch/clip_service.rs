@@ -0,0 +1,273 @@
+// File: maowbot-core/src/services/twitch/clip_service.rs
+//
+// Polls Helix for new clips of the broadcaster and posts each one once to
+// the configured Discord channel; `!clipit` clips the stream on demand and
+// posts the clip as soon as Twitch has processed it.
+
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use tracing::{debug, info, warn};
+
+use maowbot_common::models::clip::PostedClip;
+use maowbot_common::models::discord::{
+    DiscordColor, DiscordEmbed, DiscordEmbedAuthor, DiscordEmbedField, DiscordEmbedFooter, DiscordEmbedImage,
+};
+use maowbot_common::traits::repository_traits::{ClipRepository, CredentialsRepository};
+
+use crate::eventbus::EventBus;
+use crate::platforms::manager::PlatformManager;
+use crate::platforms::twitch::requests::clips::ClipData;
+use crate::platforms::twitch::routing::TwitchOperation;
+use crate::services::twitch::{broadcaster_helix, BroadcasterHelix};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// How far back each poll looks. Helix can list a clip minutes after it was
+/// made, so polls overlap and posted clips are skipped by id.
+const LOOKBACK_HOURS: i64 = 24;
+/// Checks for a `!clipit` clip, `CLIP_READY_DELAY` apart, before giving up.
+const CLIP_READY_ATTEMPTS: u32 = 6;
+const CLIP_READY_DELAY: Duration = Duration::from_secs(5);
+
+/// The Discord channel clips go to.
+struct DiscordTarget {
+    account: String,
+    guild_id: String,
+    channel_id: String,
+}
+
+pub struct ClipService {
+    repo: Arc<dyn ClipRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    settings: Arc<SettingsRegistry>,
+    event_bus: Arc<EventBus>,
+    /// Held while checking, posting and recording a clip, so the poller and
+    /// `!clipit` never post the same clip twice.
+    post_lock: tokio::sync::Mutex<()>,
+}
+
+impl ClipService {
+    pub fn new(
+        repo: Arc<dyn ClipRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        settings: Arc<SettingsRegistry>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self {
+            repo,
+            credentials_repo,
+            platform_manager,
+            settings,
+            event_bus,
+            post_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Polls every `clips.poll_interval_seconds` while a Discord channel is set.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                let interval = service.settings.get_u64("clips.poll_interval_seconds").unwrap_or(120).max(30);
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(interval)) => {
+                        match service.poll().await {
+                            Ok(0) => {}
+                            Ok(posted) => info!("[Clips] posted {} new clip(s) to Discord", posted),
+                            Err(e) => debug!("[Clips] poll failed: {:?}", e),
+                        }
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Clips] poll loop stopped");
+        });
+    }
+
+    fn discord_target(&self) -> Option<DiscordTarget> {
+        let setting = |key: &str| self.settings.get(key).filter(|v| !v.trim().is_empty());
+        Some(DiscordTarget {
+            account: setting("clips.discord_account")?,
+            guild_id: setting("clips.discord_guild_id")?,
+            channel_id: setting("clips.discord_channel_id")?,
+        })
+    }
+
+    /// Posts clips made in the last day that haven't been posted yet, oldest
+    /// first. Returns how many were posted.
+    pub async fn poll(&self) -> Result<usize, Error> {
+        if self.discord_target().is_none() {
+            return Ok(0);
+        }
+        let BroadcasterHelix { broadcaster_id, client, .. } = broadcaster_helix(&*self.credentials_repo).await?;
+        let since = Utc::now() - chrono::Duration::hours(LOOKBACK_HOURS);
+        let mut clips = client.fetch_clips_since(&broadcaster_id, since).await?;
+
+        let ids: Vec<String> = clips.iter().map(|c| c.id.clone()).collect();
+        let posted = self.repo.posted_clip_ids(&ids).await?;
+        unposted_oldest_first(&mut clips, &posted);
+
+        let mut count = 0;
+        for clip in &clips {
+            match self.post_clip(clip, None).await {
+                Ok(true) => count += 1,
+                Ok(false) => {}
+                Err(e) => warn!("[Clips] could not post clip {}: {:?}", clip.id, e),
+            }
+        }
+        Ok(count)
+    }
+
+    /// Clips the live stream now and returns the clip's URL. The clip is
+    /// posted to Discord in the background once Twitch has processed it.
+    pub async fn clip_now(self: &Arc<Self>, requested_by: &str) -> Result<String, Error> {
+        // Clipped as the account clips are routed to (the bot by default)
+        let helix = self.platform_manager.twitch_accounts()?.helix_for(TwitchOperation::Clips).await?;
+        let created = helix.client.create_clip(helix.channel_id()?).await?;
+        info!("[Clips] {} clipped the stream: {}", requested_by, created.id);
+
+        let service = self.clone();
+        let clip_id = created.id.clone();
+        let requested_by = requested_by.to_string();
+        tokio::spawn(async move {
+            for _ in 0..CLIP_READY_ATTEMPTS {
+                tokio::time::sleep(CLIP_READY_DELAY).await;
+                match helix.client.fetch_clip(&clip_id).await {
+                    Ok(Some(clip)) => {
+                        if let Err(e) = service.post_clip(&clip, Some(&requested_by)).await {
+                            warn!("[Clips] could not post clip {}: {:?}", clip_id, e);
+                        }
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => debug!("[Clips] clip {} not ready: {:?}", clip_id, e),
+                }
+            }
+            warn!("[Clips] clip {} was not ready in time; the next poll will post it", clip_id);
+        });
+
+        Ok(format!("https://clips.twitch.tv/{}", created.id))
+    }
+
+    /// Posts a clip to Discord and records it. False when it was already
+    /// posted or no Discord channel is set.
+    async fn post_clip(&self, clip: &ClipData, requested_by: Option<&str>) -> Result<bool, Error> {
+        let Some(target) = self.discord_target() else { return Ok(false) };
+        let _guard = self.post_lock.lock().await;
+        if !self.repo.posted_clip_ids(std::slice::from_ref(&clip.id)).await?.is_empty() {
+            return Ok(false);
+        }
+
+        let embed = clip_embed(clip, requested_by);
+        self.platform_manager
+            .send_discord_embed(&target.account, &target.guild_id, &target.channel_id, &embed, None)
+            .await?;
+
+        self.repo.insert_clip(&PostedClip {
+            clip_id: clip.id.clone(),
+            broadcaster_id: clip.broadcaster_id.clone(),
+            title: clip.title.clone(),
+            creator_name: clip.creator_name.clone(),
+            url: clip.url.clone(),
+            created_at: clip.created_at,
+            posted_at: Utc::now(),
+            requested_by: requested_by.map(str::to_string),
+        }).await
+    }
+
+    pub async fn list_clips(&self, limit: i64) -> Result<Vec<PostedClip>, Error> {
+        self.repo.list_clips(limit).await
+    }
+}
+
+/// Drops clips already posted and orders the rest by when they were made.
+fn unposted_oldest_first(clips: &mut Vec<ClipData>, posted: &[String]) {
+    clips.retain(|c| !posted.contains(&c.id));
+    clips.sort_by_key(|c| c.created_at);
+}
+
+fn clip_embed(clip: &ClipData, requested_by: Option<&str>) -> DiscordEmbed {
+    let mut embed = DiscordEmbed::new();
+    embed.title = Some(if clip.title.is_empty() { "New clip".to_string() } else { clip.title.clone() });
+    embed.url = Some(clip.url.clone());
+    embed.color = Some(DiscordColor::TWITCH_PURPLE);
+    embed.timestamp = Some(clip.created_at);
+    embed.author = Some(DiscordEmbedAuthor {
+        name: format!("Clipped by {}", requested_by.unwrap_or(&clip.creator_name)),
+        url: None,
+        icon_url: None,
+    });
+    embed.image = Some(DiscordEmbedImage { url: clip.thumbnail_url.clone() });
+    embed.fields.push(DiscordEmbedField {
+        name: "Length".to_string(),
+        value: format!("{:.0}s", clip.duration),
+        inline: true,
+    });
+    if !clip.video_id.is_empty() {
+        embed.fields.push(DiscordEmbedField {
+            name: "VOD".to_string(),
+            value: format!("https://www.twitch.tv/videos/{}", clip.video_id),
+            inline: true,
+        });
+    }
+    embed.footer = Some(DiscordEmbedFooter {
+        text: format!("twitch.tv/{}", clip.broadcaster_name),
+        icon_url: None,
+    });
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn clip(id: &str, minute: u32) -> ClipData {
+        ClipData {
+            id: id.to_string(),
+            url: format!("https://clips.twitch.tv/{}", id),
+            broadcaster_id: "1".to_string(),
+            broadcaster_name: "maowcaster".to_string(),
+            creator_name: "viewer".to_string(),
+            video_id: String::new(),
+            title: String::new(),
+            view_count: 0,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 16, 12, minute, 0).unwrap(),
+            thumbnail_url: "https://example.invalid/thumb.jpg".to_string(),
+            duration: 29.6,
+        }
+    }
+
+    #[test]
+    fn test_posts_only_new_clips_oldest_first() {
+        let mut clips = vec![clip("late", 30), clip("posted", 10), clip("early", 5)];
+        unposted_oldest_first(&mut clips, &["posted".to_string()]);
+        let ids: Vec<&str> = clips.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["early", "late"]);
+    }
+
+    #[test]
+    fn test_embed_credits_the_chatter_and_links_the_vod() {
+        let polled = clip_embed(&clip("a", 0), None);
+        assert_eq!(polled.title.as_deref(), Some("New clip"));
+        assert_eq!(polled.author.unwrap().name, "Clipped by viewer");
+        assert_eq!(polled.fields.len(), 1);
+        assert_eq!(polled.fields[0].value, "30s");
+
+        let mut with_vod = clip("b", 0);
+        with_vod.title = "big play".to_string();
+        with_vod.video_id = "42".to_string();
+        let requested = clip_embed(&with_vod, Some("maowfan"));
+        assert_eq!(requested.title.as_deref(), Some("big play"));
+        assert_eq!(requested.author.unwrap().name, "Clipped by maowfan");
+        assert_eq!(requested.fields[1].value, "https://www.twitch.tv/videos/42");
+    }
+}
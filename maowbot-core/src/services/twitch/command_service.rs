@@ -1,27 +1,37 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use chrono::{Utc, DateTime};
+use std::time::Instant;
+use chrono::{Utc, DateTime, Duration};
 use uuid::Uuid;
 use tracing::{debug, warn, error};
 use maowbot_common::models::{Command, CommandUsage};
+use maowbot_common::models::platform::Platform;
 use maowbot_common::models::platform::Platform::TwitchIRC;
 use maowbot_common::models::user::User;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
 use maowbot_common::traits::repository_traits::{
     BotConfigRepository,
     CommandRepository,
     CommandUsageRepository,
     CredentialsRepository,
-    UserRepo
 };
 use crate::plugins::manager::PluginManager;
-use maowbot_common::models::platform::PlatformCredential;
 use crate::Error;
-use crate::services::twitch::builtin_commands::handle_builtin_command;
+use crate::i18n;
+use crate::services::command_spec::{CommandArgs, CommandSpec};
+use crate::services::twitch::builtin_commands::find_builtin;
+use crate::services::twitch::send_whisper;
+use crate::platforms::twitch::routing::TwitchOperation;
 use crate::services::user_service::UserService;
+use crate::services::identity_link::{LinkCodeError, LinkOutcome, LINK_CODE_TTL_MINUTES};
+use crate::settings::SettingsRegistry;
+use crate::services::workspace_router::WorkspaceRouter;
 use crate::services::message_sender::{MessageSender, MessageResponse};
 
 /// Context passed to built-in command handlers.
 pub struct CommandContext<'a> {
+    /// e.g. "twitch-irc"
+    pub platform: &'a str,
     pub channel: &'a str,
     pub user_roles: &'a [String],
     pub is_stream_online: bool,
@@ -31,21 +41,186 @@ pub struct CommandContext<'a> {
 
     pub credentials_repo: &'a Arc<dyn CredentialsRepository + Send + Sync>,
     pub bot_config_repo: &'a Arc<dyn BotConfigRepository + Send + Sync>,
+    pub settings: &'a Arc<SettingsRegistry>,
     pub plugin_manager: Option<Arc<PluginManager>>,
 }
 
+impl CommandContext<'_> {
+    /// A response in the channel's language.
+    pub fn text(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let localizer = self.plugin_manager.as_ref().and_then(|pm| pm.localizer.clone());
+        i18n::text_for(localizer.as_deref(), self.platform, self.channel, key, args)
+    }
+}
+
 /// Response from command handlers: multiple lines + which credential we used + which channel.
 /// This is now just a type alias for the shared MessageResponse type
 pub type CommandResponse = MessageResponse;
 
-/// Tracks command cooldowns globally, etc.
+/// Looks up a chat string by key and fills in its `{placeholders}`.
+type Translate<'a> = dyn Fn(&str, &[(&str, &str)]) -> String + Sync + 'a;
+
+/// Entries kept before expired cooldowns are swept out.
+const COOLDOWN_SWEEP_THRESHOLD: usize = 4096;
+
+/// Tracks when each command may be used again: globally, per user and per
+/// chat role. Entries hold the time the cooldown ends.
 #[derive(Debug, Default)]
 pub struct CooldownTracker {
-    last_global_use: HashMap<Uuid, DateTime<Utc>>,
+    global_ready_at: HashMap<Uuid, DateTime<Utc>>,
+    user_ready_at: HashMap<(Uuid, Uuid), DateTime<Utc>>,
+    role_ready_at: HashMap<(Uuid, String), DateTime<Utc>>,
+    /// Users already told about the cooldown blocking them, until it ends
+    warned_until: HashMap<(Uuid, Uuid), DateTime<Utc>>,
+}
+
+/// Discord DMs arrive on a "(DM <channel id>)" channel; answers go to the id.
+fn discord_dm_channel(channel: &str) -> Option<&str> {
+    channel.strip_prefix("(DM ").and_then(|c| c.strip_suffix(')'))
+}
+
+/// Private replies are whispers, so only Twitch chat commands can send them.
+fn replies_privately(cmd: &Command, platform: &str) -> bool {
+    cmd.reply_privately && platform.eq_ignore_ascii_case("twitch-irc")
+}
+
+fn is_moderator(roles: &[String]) -> bool {
+    roles.iter().any(|r| matches!(r.to_lowercase().as_str(), "mod" | "moderator" | "broadcaster"))
+}
+
+/// Whether the roles meet a command's `min_role`; the broadcaster counts as a moderator.
+fn has_role(roles: &[String], min_role: &str) -> bool {
+    match min_role.to_lowercase().as_str() {
+        "everyone" => true,
+        "mod" | "moderator" => is_moderator(roles),
+        needed => roles.iter().any(|r| r.to_lowercase() == needed),
+    }
+}
+
+/// An active command with no cooldowns or reply options.
+fn new_command(platform: &str, command_name: &str, min_role: &str) -> Command {
+    let now = Utc::now();
+    Command {
+        command_id: Uuid::new_v4(),
+        active_credential_id: None,
+        platform: platform.to_string(),
+        command_name: command_name.to_string(),
+        min_role: min_role.to_string(),
+        is_active: true,
+        created_at: now,
+        updated_at: now,
+        cooldown_seconds: 0,
+        cooldown_warnonce: false,
+        user_cooldown_seconds: 0,
+        role_cooldowns: Default::default(),
+        cooldown_bypass_mods: false,
+        reply_privately: false,
+        reply_to_message: false,
+        response: None,
+        respond_with_credential: None,
+        stream_online_only: false,
+        stream_offline_only: false,
+    }
+}
+
+/// A custom command's response with its placeholders filled in. `{touser}`
+/// is the first argument without its '@', or the caller when there is none.
+pub fn fill_response(template: &str, user: &str, channel: &str, args: &str) -> String {
+    let touser = args.split_whitespace().next().map(|a| a.trim_start_matches('@')).unwrap_or(user);
+    i18n::render(template, &[
+        ("user", user),
+        ("touser", touser),
+        ("channel", channel.trim_start_matches('#')),
+        ("args", args.trim()),
+    ])
+}
+
+/// How a command's arguments arrived.
+pub enum CommandInput<'a> {
+    /// The text after `!name` in a chat message
+    Chat(&'a str),
+    /// Named option values, e.g. from a Discord slash command
+    Options(&'a [(String, String)]),
+}
+
+impl CooldownTracker {
+    /// Checks every cooldown that applies to `user_id`. If none is running the
+    /// use is recorded and `None` returned; otherwise nothing is recorded and
+    /// the time the last blocking cooldown ends is returned.
+    pub fn try_use(
+        &mut self,
+        cmd: &Command,
+        user_id: Uuid,
+        user_roles: &[String],
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        if cmd.cooldown_bypass_mods && is_moderator(user_roles) {
+            return None;
+        }
+        let roles: Vec<(String, i32)> = user_roles.iter()
+            .map(|r| r.to_lowercase())
+            .filter_map(|r| cmd.role_cooldowns.get(&r).map(|secs| (r, *secs)))
+            .filter(|(_, secs)| *secs > 0)
+            .collect();
+
+        let blocked_until = [
+            self.global_ready_at.get(&cmd.command_id),
+            self.user_ready_at.get(&(cmd.command_id, user_id)),
+        ]
+            .into_iter()
+            .flatten()
+            .copied()
+            .chain(roles.iter().filter_map(|(r, _)| self.role_ready_at.get(&(cmd.command_id, r.clone())).copied()))
+            .filter(|ready_at| *ready_at > now)
+            .max();
+        if blocked_until.is_some() {
+            return blocked_until;
+        }
+
+        let after = |secs: i32| now + Duration::seconds(secs as i64);
+        if cmd.cooldown_seconds > 0 {
+            self.global_ready_at.insert(cmd.command_id, after(cmd.cooldown_seconds));
+        }
+        if cmd.user_cooldown_seconds > 0 {
+            self.user_ready_at.insert((cmd.command_id, user_id), after(cmd.user_cooldown_seconds));
+        }
+        for (role, secs) in roles {
+            self.role_ready_at.insert((cmd.command_id, role), after(secs));
+        }
+        self.sweep(now);
+        None
+    }
+
+    /// Whether a chatter blocked until `until` should be told. With
+    /// `cooldown_warnonce` only the first attempt during a cooldown is.
+    pub fn should_warn(&mut self, cmd: &Command, user_id: Uuid, until: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        if !cmd.cooldown_warnonce {
+            return true;
+        }
+        let key = (cmd.command_id, user_id);
+        if self.warned_until.get(&key).is_some_and(|t| *t > now) {
+            return false;
+        }
+        self.warned_until.insert(key, until);
+        true
+    }
+
+    fn sweep(&mut self, now: DateTime<Utc>) {
+        if self.user_ready_at.len() + self.role_ready_at.len() + self.warned_until.len() < COOLDOWN_SWEEP_THRESHOLD {
+            return;
+        }
+        self.global_ready_at.retain(|_, t| *t > now);
+        self.user_ready_at.retain(|_, t| *t > now);
+        self.role_ready_at.retain(|_, t| *t > now);
+        self.warned_until.retain(|_, t| *t > now);
+    }
 }
 
 /// The main service for handling custom commands, building on a commands-cache in memory.
+/// Each channel's commands, config and credentials come from the workspace it's
+/// routed to (see `WorkspaceRouter`); the repositories below are the default workspace's.
 pub struct CommandService {
+    workspaces: Arc<WorkspaceRouter>,
     command_repo: Arc<dyn CommandRepository + Send + Sync>,
     usage_repo: Arc<dyn CommandUsageRepository + Send + Sync>,
     pub credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
@@ -53,6 +228,7 @@ pub struct CommandService {
     cooldowns: Arc<Mutex<CooldownTracker>>,
 
     pub bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
+    pub settings: Arc<SettingsRegistry>,
     
     // Platform manager reference for sending messages
     pub platform_manager: Arc<crate::platforms::manager::PlatformManager>,
@@ -67,16 +243,24 @@ pub struct CommandService {
     commands_cache: Arc<Mutex<HashMap<String, Command>>>,
 }
 
+fn cache_key(workspace_id: Uuid, platform: &str, command_name: &str) -> String {
+    format!("{}|{}|{}", workspace_id, platform.to_lowercase(), command_name.to_lowercase())
+}
+
 impl CommandService {
     pub fn new(
-        command_repo: Arc<dyn CommandRepository + Send + Sync>,
+        workspaces: Arc<WorkspaceRouter>,
         usage_repo: Arc<dyn CommandUsageRepository + Send + Sync>,
-        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
         user_service: Arc<UserService>,
-        bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
+        settings: Arc<SettingsRegistry>,
         platform_manager: Arc<crate::platforms::manager::PlatformManager>,
     ) -> Self {
         debug!("Initializing CommandService");
+        let default_repos = workspaces.repos(DEFAULT_WORKSPACE_ID);
+        let command_repo = default_repos.commands;
+        let bot_config_repo = default_repos.bot_config;
+        // Replies go out as accounts of any workspace
+        let credentials_repo = workspaces.credentials();
         
         // Create MessageSender instance
         let message_sender = MessageSender::new(
@@ -85,12 +269,14 @@ impl CommandService {
         );
 
         let svc = Self {
+            workspaces,
             command_repo,
             usage_repo,
             credentials_repo,
             user_service,
             cooldowns: Arc::new(Mutex::new(CooldownTracker::default())),
             bot_config_repo,
+            settings,
             platform_manager,
             message_sender,
             commands_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -101,26 +287,34 @@ impl CommandService {
         svc
     }
 
-    /// Re-fetch all commands from the DB and store them in our local HashMap,
-    /// keyed by `(platform.to_lowercase(), command_name.to_lowercase())`.
+    /// Re-fetch all commands of every workspace from the DB and store them in our
+    /// local HashMap, keyed by `(workspace, platform, command_name)` (see `cache_key`).
     pub fn reload_commands_cache(&self) {
-        let command_repo = self.command_repo.clone();
+        let workspace_ids = match futures_lite::future::block_on(self.workspaces.workspace_ids()) {
+            Ok(ids) => ids,
+            Err(e) => {
+                error!("Error listing workspaces => {:?}", e);
+                vec![DEFAULT_WORKSPACE_ID]
+            }
+        };
         let mut cache_guard = self.commands_cache.lock().unwrap();
         cache_guard.clear();
 
         // We handle multiple platforms, so let's do a quick gather:
         // (In practice you might call list_commands for each platform or fetch all at once.)
         let platforms = ["twitch-irc", "twitch", "discord"]; // etc.
-        for &pf in &platforms {
-            match futures_lite::future::block_on(command_repo.list_commands(pf)) {
-                Ok(cmds) => {
-                    for c in cmds {
-                        let key = format!("{}|{}", c.platform.to_lowercase(), c.command_name.to_lowercase());
-                        cache_guard.insert(key, c);
+        for workspace_id in workspace_ids {
+            let command_repo = self.workspaces.repos(workspace_id).commands;
+            for &pf in &platforms {
+                match futures_lite::future::block_on(command_repo.list_commands(pf)) {
+                    Ok(cmds) => {
+                        for c in cmds {
+                            cache_guard.insert(cache_key(workspace_id, &c.platform, &c.command_name), c);
+                        }
+                    }
+                    Err(e) => {
+                        error!("Error loading commands for {} in workspace {} => {:?}", pf, workspace_id, e);
                     }
-                }
-                Err(e) => {
-                    error!("Error loading commands for {} => {:?}", pf, e);
                 }
             }
         }
@@ -128,9 +322,10 @@ impl CommandService {
         debug!("reload_commands_cache => loaded {} commands total", cache_guard.len());
     }
 
-    /// Lookup a command from our in-memory cache by (platform, command_name).
-    fn find_command_in_cache(&self, platform: &str, command_name: &str) -> Option<Command> {
-        let key = format!("{}|{}", platform.to_lowercase(), command_name.to_lowercase());
+    /// Lookup a command from our in-memory cache by (platform, command_name), in
+    /// the workspace `channel` is routed to.
+    fn find_command_in_cache(&self, platform: &str, channel: &str, command_name: &str) -> Option<Command> {
+        let key = cache_key(self.workspaces.workspace_for(platform, channel), platform, command_name);
         let lock = self.commands_cache.lock().unwrap();
         lock.get(&key).cloned()
     }
@@ -141,11 +336,16 @@ impl CommandService {
         platform: &str,
         channel: &str,
         user_id: Uuid,
+        platform_user_id: &str,
         user_roles: &[String],
         message_text: &str,
         is_stream_online: bool,
     ) -> Result<Option<CommandResponse>, Error> {
         debug!("handle_chat_line() received message: '{}'", message_text);
+        let localizer = self.platform_manager.plugin_manager().and_then(|pm| pm.localizer.clone());
+        let tr = |key: &str, args: &[(&str, &str)]| {
+            i18n::text_for(localizer.as_deref(), platform, channel, key, args)
+        };
 
         // -----------------------------------------------------------------
         // 1) Must start with '!'
@@ -153,7 +353,7 @@ impl CommandService {
         if !message_text.trim().starts_with('!') {
             return Ok(None);
         }
-        let parts: Vec<&str> = message_text.trim().split_whitespace().collect();
+        let parts: Vec<&str> = message_text.split_whitespace().collect();
         let cmd_part = parts[0].trim_start_matches('!');
         let args = if parts.len() > 1 {
             parts[1..].join(" ")
@@ -173,7 +373,7 @@ impl CommandService {
 
                 if !sent {
                     self.message_sender
-                        .send_twitch_message(channel, "No continuation available.", None, user_id)
+                        .send_twitch_message(channel, &tr("command.no_continuation", &[]), None, user_id)
                         .await
                         .ok();
                 }
@@ -189,7 +389,7 @@ impl CommandService {
                     self.message_sender
                         .send_twitch_message(
                             channel,
-                            "No recent AI message found. Sources not available.",
+                            &tr("command.no_sources", &[]),
                             None,
                             user_id,
                         )
@@ -198,91 +398,217 @@ impl CommandService {
                 }
                 return Ok(None);
             }
+            "link" if platform.eq_ignore_ascii_case("twitch-irc") || platform.eq_ignore_ascii_case("discord") => {
+                let text = self.handle_link_command(platform, channel, user_id, platform_user_id, &args, &tr).await;
+                if platform.eq_ignore_ascii_case("discord") {
+                    return Ok(Some(CommandResponse {
+                        texts: vec![text],
+                        respond_credential_id: None,
+                        platform: platform.to_string(),
+                        channel: discord_dm_channel(channel).unwrap_or(channel).to_string(),
+                        reply_to_message: false,
+                    }));
+                }
+                self.message_sender
+                    .send_twitch_message(channel, &text, None, user_id)
+                    .await
+                    .ok();
+                return Ok(None);
+            }
             _ => { /* fall through to DB commands */ }
         }
 
         // -----------------------------------------------------------------
-        // 3) Look up command in cache / DB
+        // 3) Look up the command: its row, or a built-in's defaults
         // -----------------------------------------------------------------
-        let cmd_opt = self.find_command_in_cache(platform, cmd_part);
-        let cmd = match cmd_opt {
-            Some(c) => c,
-            None => {
-                debug!("No command found matching '{}'", cmd_part);
-                return Ok(None);
-            }
+        let Some((cmd, spec)) = self.resolve_command(platform, channel, cmd_part) else {
+            debug!("No command found matching '{}'", cmd_part);
+            return Ok(None);
         };
         if !cmd.is_active {
             debug!("Command '{}' is inactive.", cmd.command_name);
             return Ok(None);
         }
 
-        // 3) Check roles
-        if cmd.min_role.to_lowercase() != "everyone" {
-            let needed = cmd.min_role.to_lowercase();
-            let has_role = user_roles.iter().any(|r| r.to_lowercase() == needed);
-            if !has_role {
-                return Ok(Some(CommandResponse {
-                    texts: vec![format!("You lack the required role '{}' to use this.", cmd.min_role)],
-                    respond_credential_id: cmd.respond_with_credential,
-                    platform: cmd.platform.clone(),
-                    channel: channel.to_string(),
-                }));
-            }
+        self.run_command(
+            platform,
+            channel,
+            user_id,
+            platform_user_id,
+            user_roles,
+            is_stream_online,
+            cmd,
+            spec,
+            CommandInput::Chat(&args),
+        ).await
+    }
+
+    /// Runs a Discord slash command, or a simulated one. Unlike chat, every
+    /// use gets an answer: unknown commands and cooldowns are answered too, and
+    /// a command that ran without saying anything answers `command.done`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn handle_slash_command(
+        &self,
+        platform: &str,
+        channel: &str,
+        user_id: Uuid,
+        platform_user_id: &str,
+        user_roles: &[String],
+        command_name: &str,
+        options: &[(String, String)],
+        is_stream_online: bool,
+    ) -> Result<Vec<String>, Error> {
+        let cmd = self.resolve_command(platform, channel, command_name).filter(|(cmd, _)| cmd.is_active);
+        let localizer = self.platform_manager.plugin_manager().and_then(|pm| pm.localizer.clone());
+        let Some((cmd, spec)) = cmd else {
+            let text = i18n::text_for(localizer.as_deref(), platform, channel, "command.unknown", &[("command", command_name)]);
+            return Ok(vec![text]);
+        };
+        let response = self.run_command(
+            platform,
+            channel,
+            user_id,
+            platform_user_id,
+            user_roles,
+            is_stream_online,
+            cmd,
+            spec,
+            CommandInput::Options(options),
+        ).await?;
+        let texts = response.map(|r| r.texts).unwrap_or_default();
+        if texts.is_empty() {
+            return Ok(vec![i18n::text_for(localizer.as_deref(), platform, channel, "command.done", &[])]);
         }
+        Ok(texts)
+    }
 
-        // 4) Stream constraints
+    /// The command `name` on `platform` in `channel`'s workspace: its row if it
+    /// has one, otherwise the defaults of a built-in available there. Defaults
+    /// carry a nil id.
+    fn resolve_command(&self, platform: &str, channel: &str, name: &str) -> Option<(Command, Option<&'static CommandSpec>)> {
+        let spec = find_builtin(name);
+        if let Some(cmd) = self.find_command_in_cache(platform, channel, name) {
+            return Some((cmd, spec));
+        }
+        let spec = spec.filter(|s| s.available_on(platform))?;
+        let mut cmd = new_command(platform, spec.name, spec.min_role);
+        cmd.command_id = Uuid::nil();
+        Some((cmd, Some(spec)))
+    }
+
+    /// Checks roles, stream state, arguments and cooldowns, then runs the
+    /// command's built-in logic and logs the use.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_command(
+        &self,
+        platform: &str,
+        channel: &str,
+        user_id: Uuid,
+        platform_user_id: &str,
+        user_roles: &[String],
+        is_stream_online: bool,
+        cmd: Command,
+        spec: Option<&'static CommandSpec>,
+        input: CommandInput<'_>,
+    ) -> Result<Option<CommandResponse>, Error> {
+        let localizer = self.platform_manager.plugin_manager().and_then(|pm| pm.localizer.clone());
+        let tr = |key: &str, args: &[(&str, &str)]| {
+            i18n::text_for(localizer.as_deref(), platform, channel, key, args)
+        };
+        let slash = matches!(input, CommandInput::Options(_));
+
+        // 1) Check roles
+        if !has_role(user_roles, &cmd.min_role) {
+            return Ok(Some(CommandResponse {
+                texts: vec![tr("command.missing_role", &[("role", &cmd.min_role)])],
+                respond_credential_id: cmd.respond_with_credential,
+                platform: cmd.platform.clone(),
+                channel: channel.to_string(),
+                reply_to_message: cmd.reply_to_message,
+            }));
+        }
+
+        // 2) Stream constraints
         if cmd.stream_online_only && !is_stream_online {
             return Ok(Some(CommandResponse {
-                texts: vec![format!("Command {} can only be used when stream is online.", cmd.command_name)],
+                texts: vec![tr("command.online_only", &[("command", &cmd.command_name)])],
                 respond_credential_id: cmd.respond_with_credential,
                 platform: cmd.platform.clone(),
                 channel: channel.to_string(),
+                reply_to_message: cmd.reply_to_message,
             }));
         }
         if cmd.stream_offline_only && is_stream_online {
             return Ok(Some(CommandResponse {
-                texts: vec![format!("Command {} can only be used when stream is offline.", cmd.command_name)],
+                texts: vec![tr("command.offline_only", &[("command", &cmd.command_name)])],
                 respond_credential_id: cmd.respond_with_credential,
                 platform: cmd.platform.clone(),
                 channel: channel.to_string(),
+                reply_to_message: cmd.reply_to_message,
             }));
         }
 
-        // 5) Check cooldown
-        let now = Utc::now();
-        {
-            let mut cd_lock = self.cooldowns.lock().unwrap();
-            if let Some(last_time) = cd_lock.last_global_use.get(&cmd.command_id) {
-                let elapsed = now.signed_duration_since(*last_time).num_seconds();
-                let remain = cmd.cooldown_seconds as i64 - elapsed;
-                if remain > 0 {
-                    return Ok(Some(CommandResponse {
-                        texts: vec![format!("Command {} is on cooldown. Wait {}s.", cmd.command_name, remain)],
-                        respond_credential_id: cmd.respond_with_credential,
-                        platform: cmd.platform.clone(),
-                        channel: channel.to_string(),
-                    }));
-                }
+        // 3) Parse arguments against the built-in's schema; a bad use doesn't start a cooldown
+        let parsed = match (spec, &input) {
+            (Some(spec), CommandInput::Chat(line)) => spec.parse_line(line),
+            (Some(spec), CommandInput::Options(options)) => spec.parse_options(options),
+            (None, CommandInput::Chat(line)) => Ok(CommandArgs::from_raw(line)),
+            (None, CommandInput::Options(options)) => {
+                let values: Vec<&str> = options.iter().map(|(_, v)| v.as_str()).collect();
+                Ok(CommandArgs::from_raw(&values.join(" ")))
             }
-            cd_lock.last_global_use.insert(cmd.command_id, now);
-        }
+        };
+        let args = match parsed {
+            Ok(args) => args,
+            Err(e) => {
+                let usage = spec.map(|s| s.usage(if slash { "/" } else { "!" })).unwrap_or_default();
+                return Ok(Some(CommandResponse {
+                    texts: vec![tr("command.bad_arguments", &[("error", &e.to_string()), ("usage", &usage)])],
+                    respond_credential_id: cmd.respond_with_credential,
+                    platform: cmd.platform.clone(),
+                    channel: channel.to_string(),
+                    reply_to_message: cmd.reply_to_message,
+                }));
+            }
+        };
 
-        // 6) Insert usage
-        let usage = CommandUsage {
-            usage_id: Uuid::new_v4(),
-            command_id: cmd.command_id,
-            user_id,
-            used_at: now,
-            channel: channel.to_string(),
-            usage_text: args.clone(),
-            metadata: None,
+        // 4) Check cooldowns
+        let now = Utc::now();
+        let blocked = {
+            let mut cd_lock = self.cooldowns.lock().unwrap();
+            cd_lock.try_use(&cmd, user_id, user_roles, now)
+                .map(|until| (until, cd_lock.should_warn(&cmd, user_id, until, now)))
         };
-        if let Err(e) = self.usage_repo.insert_usage(&usage).await {
-            error!("Error logging command usage: {:?}", e);
+        if let Some((until, warn)) = blocked {
+            let remain = ((until - now).num_milliseconds() + 999) / 1000;
+            debug!("Command '{}' is on cooldown for {} ({}s left)", cmd.command_name, user_id, remain);
+            let text = tr("command.cooldown", &[("command", &cmd.command_name), ("seconds", &remain.to_string())]);
+            let feedback = if slash {
+                "chat".to_string()
+            } else if !warn {
+                return Ok(None);
+            } else {
+                self.settings.get("commands.cooldown_feedback").unwrap_or_default()
+            };
+            return match feedback.as_str() {
+                "chat" => Ok(Some(CommandResponse {
+                    texts: vec![text],
+                    respond_credential_id: self.pick_response_credential_id(&cmd, channel, user_id).await?,
+                    platform: cmd.platform.clone(),
+                    channel: channel.to_string(),
+                    reply_to_message: cmd.reply_to_message,
+                })),
+                "whisper" if platform.eq_ignore_ascii_case("twitch-irc") => {
+                    if let Err(e) = send_whisper(&self.platform_manager, platform_user_id, &text).await {
+                        warn!("Could not whisper cooldown notice to {} => {:?}", platform_user_id, e);
+                    }
+                    Ok(None)
+                }
+                _ => Ok(None),
+            };
         }
 
-        // 7) Load user from DB
+        // 5) Load user from DB
         let user_opt = self.user_service.user_manager.user_repo.get(user_id).await?;
         let user = user_opt.unwrap_or(User {
             user_id,
@@ -292,132 +618,225 @@ impl CommandService {
             is_active: true,
         });
 
-        // 8) Build context
+        // 6) Build context, with the config and credentials of the channel's workspace
+        let scoped = self.workspaces.repos_for(platform, channel);
         let mut ctx = CommandContext {
+            platform,
             channel,
             user_roles,
             is_stream_online,
             user_service: &self.user_service,
             respond_credential_id: cmd.respond_with_credential,
             respond_credential_name: None,
-            credentials_repo: &self.credentials_repo,
-            bot_config_repo: &self.bot_config_repo,
+            credentials_repo: &scoped.credentials,
+            bot_config_repo: &scoped.bot_config,
+            settings: &self.settings,
             plugin_manager: self.platform_manager.plugin_manager(),
         };
 
         // If there's a respond_with_credential, see if we can load that credential’s user_name
         if let Some(cid) = cmd.respond_with_credential {
-            if let Ok(Some(cred)) = self.credentials_repo.get_credential_by_id(cid).await {
+            if let Ok(Some(cred)) = scoped.credentials.get_credential_by_id(cid).await {
                 ctx.respond_credential_name = Some(cred.user_name.clone());
             }
         }
 
-        // Check for special commands: !sources and !continue
-        match cmd_part.to_lowercase().as_str() {
-            "continue" => {
-                let sent = self
-                    .message_sender
-                    .handle_continue_command(channel, None, user_id)
-                    .await?;
-
-                if !sent {
-                    // Gracefully inform the user – quick inline response
-                    self.message_sender
-                        .send_twitch_message(
-                            channel,
-                            "No continuation available.",
-                            None,
-                            user_id,
-                        )
-                        .await
-                        .ok();
-                }
-                return Ok(None); // Already handled
-            }
-            "sources" => {
-                let sent = self
-                    .message_sender
-                    .handle_sources_command(channel, None, user_id)
-                    .await?;
-
-                if !sent {
-                    self.message_sender
-                        .send_twitch_message(
-                            channel,
-                            "No recent AI message found. Sources not available.",
-                            None,
-                            user_id,
-                        )
-                        .await
-                        .ok();
-                }
-                return Ok(None);
+        // 7) Run the built-in logic, logging the use with how long it took
+        let started = Instant::now();
+        let handled = match spec {
+            Some(spec) => (spec.handler)(&cmd, &ctx, &user, &args).await.map(Some),
+            None => Ok(None),
+        };
+        // Built-in defaults have no row for the usage to point at
+        if !cmd.command_id.is_nil() {
+            let usage = CommandUsage {
+                usage_id: Uuid::new_v4(),
+                command_id: cmd.command_id,
+                user_id,
+                platform: platform.to_string(),
+                used_at: now,
+                channel: channel.to_string(),
+                usage_text: args.raw().to_string(),
+                metadata: None,
+                duration_ms: Some(i32::try_from(started.elapsed().as_millis()).unwrap_or(i32::MAX)),
+                error_message: handled.as_ref().err().map(|e| e.to_string()),
+            };
+            if let Err(e) = self.usage_repo.insert_usage(&usage).await {
+                error!("Error logging command usage: {:?}", e);
             }
-            _ => { /* fallthrough to DB-defined commands */ }
         }
-        
-        // 9) Check for built-in logic
-        if let Some(response_str) = handle_builtin_command(&cmd, &ctx, &user, &args).await? {
+
+        if let Some(response_str) = handled? {
             let lines: Vec<String> = response_str
                 .split("<SPLIT>")
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .collect();
 
+            if replies_privately(&cmd, platform) {
+                self.whisper_lines(platform_user_id, &lines).await;
+                return Ok(None);
+            }
+
             // *Now* figure out which credential we will respond *from*.
-            let actual_respond_cred_id = self.pick_response_credential_id(&cmd, user_id).await?;
+            let actual_respond_cred_id = self.pick_response_credential_id(&cmd, channel, user_id).await?;
             return Ok(Some(CommandResponse {
                 texts: lines,
                 respond_credential_id: actual_respond_cred_id,
                 platform: cmd.platform.clone(),
                 channel: channel.to_string(),
+                reply_to_message: cmd.reply_to_message,
             }));
         }
 
-        // 10) No built-in logic => default text
-        let actual_respond_cred_id = self.pick_response_credential_id(&cmd, user_id).await?;
+        // 8) No built-in logic => the command's own text, or the default one
+        let text = match cmd.response.as_deref().filter(|r| !r.trim().is_empty()) {
+            Some(template) => {
+                let name = user.global_username.clone().unwrap_or_else(|| platform_user_id.to_string());
+                fill_response(template, &name, channel, args.raw())
+            }
+            None => tr("command.no_logic", &[("command", &cmd.command_name)]),
+        };
+        if replies_privately(&cmd, platform) {
+            self.whisper_lines(platform_user_id, &[text]).await;
+            return Ok(None);
+        }
+        let actual_respond_cred_id = self.pick_response_credential_id(&cmd, channel, user_id).await?;
         Ok(Some(CommandResponse {
-            texts: vec![format!("Command {} recognized but no built-in logic found.", cmd.command_name)],
+            texts: vec![text],
             respond_credential_id: actual_respond_cred_id,
             platform: cmd.platform.clone(),
             channel: channel.to_string(),
+            reply_to_message: cmd.reply_to_message,
         }))
     }
 
+    /// `!link` hands out a code privately (whispered on Twitch, only in DMs on
+    /// Discord); `!link CODE` redeems one issued on the other platform.
+    async fn handle_link_command(
+        &self,
+        platform: &str,
+        channel: &str,
+        user_id: Uuid,
+        platform_user_id: &str,
+        args: &str,
+        tr: &Translate<'_>,
+    ) -> String {
+        if !self.settings.get_bool("commands.link_enabled").unwrap_or(true) {
+            return tr("link.disabled", &[]);
+        }
+        let on_discord = platform.eq_ignore_ascii_case("discord");
+        let platform_enum = if on_discord { Platform::Discord } else { TwitchIRC };
+        let minutes = LINK_CODE_TTL_MINUTES.to_string();
+
+        if let Some(code) = args.split_whitespace().next() {
+            let key = match self.user_service.link_identity_with_code(code, user_id, platform_enum).await {
+                Ok(LinkOutcome::Linked(_)) => "link.linked",
+                Ok(LinkOutcome::AlreadyLinked) => "link.already_linked",
+                Ok(LinkOutcome::Failed(LinkCodeError::Invalid)) => "link.invalid",
+                Ok(LinkOutcome::Failed(LinkCodeError::SamePlatform)) => "link.same_platform",
+                Err(e) => {
+                    error!("Linking user {} with a code failed => {:?}", user_id, e);
+                    "link.failed"
+                }
+            };
+            return tr(key, &[]);
+        }
+
+        if on_discord {
+            // A code posted in a server channel could be redeemed by anyone reading it
+            if discord_dm_channel(channel).is_none() {
+                return tr("link.dm_only", &[]);
+            }
+            let code = self.user_service.start_identity_link(user_id, platform_enum);
+            return tr("link.code_discord", &[("code", &code), ("minutes", &minutes)]);
+        }
+
+        let code = self.user_service.start_identity_link(user_id, platform_enum);
+        let name = match self.user_service.user_manager.user_repo.get(user_id).await {
+            Ok(Some(user)) => user.global_username.unwrap_or_else(|| platform_user_id.to_string()),
+            _ => platform_user_id.to_string(),
+        };
+        let text = tr("link.code_twitch", &[("code", &code), ("minutes", &minutes)]);
+        match send_whisper(&self.platform_manager, platform_user_id, &text).await {
+            Ok(()) => tr("link.whispered", &[("user", &name)]),
+            Err(e) => {
+                warn!("Could not whisper a link code to {} => {:?}", platform_user_id, e);
+                tr("link.whisper_failed", &[("user", &name)])
+            }
+        }
+    }
+
+    /// Whispers a private reply line by line; failures (missing scope, unverified
+    /// sender, recipient blocking whispers) are only logged.
+    async fn whisper_lines(&self, platform_user_id: &str, lines: &[String]) {
+        for line in lines {
+            if let Err(e) = send_whisper(&self.platform_manager, platform_user_id, line).await {
+                warn!("Could not whisper command reply to {} => {:?}", platform_user_id, e);
+                return;
+            }
+        }
+    }
+
     /// Determine which Twitch-IRC credential we should use to send the reply.
     ///
     /// The user’s new rules:
     ///  1) If `cmd.active_credential_id` is set and is a valid *bot* credential, use that.
-    ///  2) If no such valid credential, we check if there's any bot at all: pick the first we find.
-    ///  3) If no bot, pick a broadcaster (the first broadcaster we find).
-    ///  4) If no broadcaster either, use the account that actually received this message (user_id).
+    ///  2) If no such valid credential, the account chat is routed to: the first bot, or the
+    ///     broadcaster if there's no bot (`twitch.account_routing` can change this). A channel
+    ///     routed to another workspace answers with that workspace's bot or broadcaster first.
+    ///  3) If neither exists, use the account that actually received this message (user_id).
+    ///
+    /// Only credentials of the channel's workspace are considered.
     async fn pick_response_credential_id(
         &self,
         cmd: &Command,
+        channel: &str,
         message_sender_user_id: Uuid
     ) -> Result<Option<Uuid>, Error> {
+        let workspace_id = self.workspaces.workspace_for(&cmd.platform, channel);
+        let credentials_repo = self.workspaces.repos(workspace_id).credentials;
+
+        // #0: the account the command itself is set to answer as
+        if let Some(cid) = cmd.respond_with_credential {
+            if let Ok(Some(c)) = credentials_repo.get_credential_by_id(cid).await {
+                if c.platform == TwitchIRC {
+                    return Ok(Some(cid));
+                }
+            }
+        }
+
         // #1: if the command’s `active_credential_id` is set:
         if let Some(cid) = cmd.active_credential_id {
-            if let Ok(Some(c)) = self.credentials_repo.get_credential_by_id(cid).await {
+            if let Ok(Some(c)) = credentials_repo.get_credential_by_id(cid).await {
                 if c.is_bot && c.platform == TwitchIRC {
                     return Ok(Some(cid));
                 }
             }
         }
 
-        // #2: find the first “bot” Twitch‑IRC credential
-        let all_creds = self.credentials_repo.list_credentials_for_platform(&TwitchIRC).await?;
-        if let Some(bot_cred) = all_creds.iter().find(|c| c.is_bot) {
-            return Ok(Some(bot_cred.credential_id));
+        // #2: the workspace's own bot or broadcaster, the identity assigned to this
+        // channel, else whichever account chat is routed to
+        if workspace_id != DEFAULT_WORKSPACE_ID {
+            let own = credentials_repo.list_credentials_for_platform(&TwitchIRC).await?;
+            if let Some(c) = own.iter().find(|c| c.is_bot).or_else(|| own.iter().find(|c| c.is_broadcaster)) {
+                return Ok(Some(c.credential_id));
+            }
         }
-
-        // #3: find the first broadcaster
-        if let Some(broadcaster_cred) = all_creds.iter().find(|c| c.is_broadcaster) {
-            return Ok(Some(broadcaster_cred.credential_id));
+        if let Some(identities) = self.platform_manager.chat_identities() {
+            if let Some(c) = identities.credential_for(&TwitchIRC, channel).await? {
+                return Ok(Some(c.credential_id));
+            }
+        }
+        if let Ok(accounts) = self.platform_manager.twitch_accounts() {
+            match accounts.account_for(TwitchOperation::Chat).await {
+                Ok(routed) => return Ok(Some(routed.credential.credential_id)),
+                Err(e) => debug!("No routed chat account => {}", e),
+            }
         }
 
-        // #4: no bot, no broadcaster => use the same user’s own Twitch-IRC credential if it exists
-        let maybe_same_user_cred = self.credentials_repo.get_credentials(
+        // #3: no bot, no broadcaster => use the same user’s own Twitch-IRC credential if it exists
+        let maybe_same_user_cred = credentials_repo.get_credentials(
             &TwitchIRC,
             message_sender_user_id
         ).await?;
@@ -440,22 +859,7 @@ impl CommandService {
         min_role: &str,
     ) -> Result<Command, Error> {
         debug!("Creating new command for platform '{}': '{}'", platform, command_name);
-        let now = Utc::now();
-        let cmd = Command {
-            command_id: Uuid::new_v4(),
-            active_credential_id: None,
-            platform: platform.to_string(),
-            command_name: command_name.to_string(),
-            min_role: min_role.to_string(),
-            is_active: true,
-            created_at: now,
-            updated_at: now,
-            cooldown_seconds: 0,
-            cooldown_warnonce: false,
-            respond_with_credential: None,
-            stream_online_only: false,
-            stream_offline_only: false,
-        };
+        let cmd = new_command(platform, command_name, min_role);
         self.command_repo.create_command(&cmd).await?;
         // Also refresh in-memory:
         self.reload_commands_cache();
@@ -500,3 +904,67 @@ impl CommandService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(global: i32, user: i32, roles: &[(&str, i32)]) -> Command {
+        Command {
+            command_id: Uuid::new_v4(),
+            platform: "twitch-irc".to_string(),
+            command_name: "hug".to_string(),
+            min_role: "everyone".to_string(),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            cooldown_seconds: global,
+            user_cooldown_seconds: user,
+            role_cooldowns: roles.iter().map(|(r, s)| (r.to_string(), *s)).collect(),
+            cooldown_bypass_mods: true,
+            reply_privately: false,
+            reply_to_message: false,
+            response: None,
+            cooldown_warnonce: true,
+            respond_with_credential: None,
+            stream_online_only: false,
+            stream_offline_only: false,
+            active_credential_id: None,
+        }
+    }
+
+    #[test]
+    fn test_cooldowns_are_independent() {
+        let cmd = command(5, 60, &[("subscriber", 30)]);
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let sub = vec!["subscriber".to_string()];
+        let t0 = Utc::now();
+        let mut cd = CooldownTracker::default();
+
+        assert_eq!(cd.try_use(&cmd, alice, &sub, t0), None);
+        // Global cooldown blocks everyone
+        assert_eq!(cd.try_use(&cmd, bob, &[], t0 + Duration::seconds(1)), Some(t0 + Duration::seconds(5)));
+        // After it, other viewers may go; subs wait for the role cooldown
+        assert_eq!(cd.try_use(&cmd, bob, &[], t0 + Duration::seconds(6)), None);
+        assert_eq!(cd.try_use(&cmd, carol, &sub, t0 + Duration::seconds(12)), Some(t0 + Duration::seconds(30)));
+        // Alice waits for her own cooldown even once the role one is over
+        assert_eq!(cd.try_use(&cmd, alice, &sub, t0 + Duration::seconds(40)), Some(t0 + Duration::seconds(60)));
+        // Mods skip all of it
+        assert_eq!(cd.try_use(&cmd, carol, &["moderator".to_string()], t0 + Duration::seconds(41)), None);
+
+        let until = t0 + Duration::seconds(60);
+        assert!(cd.should_warn(&cmd, alice, until, t0 + Duration::seconds(40)));
+        assert!(!cd.should_warn(&cmd, alice, until, t0 + Duration::seconds(45)));
+    }
+
+    #[test]
+    fn test_only_twitch_commands_reply_privately() {
+        let mut cmd = command(0, 0, &[]);
+        assert!(!replies_privately(&cmd, "twitch-irc"));
+        cmd.reply_privately = true;
+        assert!(replies_privately(&cmd, "twitch-irc"));
+        assert!(replies_privately(&cmd, "Twitch-IRC"));
+        // Discord has no whispers; the reply goes to the channel as usual
+        assert!(!replies_privately(&cmd, "discord"));
+    }
+}
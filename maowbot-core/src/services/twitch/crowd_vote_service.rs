@@ -0,0 +1,559 @@
+// File: maowbot-core/src/services/twitch/crowd_vote_service.rs
+//
+// Crowd control votes: for a short window chat picks one of a few OSC effects
+// by typing `#2` or the option's name. A vote weighs more the longer the
+// viewer has watched the channel (one extra per `crowd.weight_hours`, capped
+// at `crowd.max_weight`). When the window ends the winner's OSC chat control
+// is toggled and `BotEvent::CrowdVoteDecided` goes out, so pipelines can run
+// the rest of the effect. Live tallies reach overlays as a `crowd_vote`
+// GameEvent. Votes are only kept in memory; a restart drops an open one.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use rand::Rng;
+use serde::Serialize;
+use serde_json::json;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::WatchtimeRepository;
+use maowbot_proto::plugs::{
+    plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse,
+};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::plugins::manager::PluginManager;
+use crate::services::message_sender::MessageSender;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use crate::i18n;
+
+/// `#1` through `#9`.
+const MAX_OPTIONS: usize = 9;
+const MIN_SECONDS: u32 = 5;
+const MAX_SECONDS: u32 = 600;
+/// How often tallies are pushed to overlays and expired votes are decided.
+const TICK: StdDuration = StdDuration::from_secs(1);
+
+/// One effect chat can vote for.
+#[derive(Debug, Clone)]
+pub struct CrowdVoteOption {
+    pub label: String,
+    /// OSC chat control toggled when this option wins
+    pub control: Option<String>,
+}
+
+/// What's needed to start a vote.
+#[derive(Debug, Clone)]
+pub struct NewCrowdVote {
+    pub title: String,
+    /// Defaults to the broadcaster's channel
+    pub channel: Option<String>,
+    pub duration_seconds: u32,
+    pub options: Vec<CrowdVoteOption>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrowdVoteStatus {
+    Open,
+    Decided,
+    /// The window ended without a single vote
+    NoVotes,
+    Cancelled,
+}
+
+impl CrowdVoteStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CrowdVoteStatus::Open => "open",
+            CrowdVoteStatus::Decided => "decided",
+            CrowdVoteStatus::NoVotes => "no_votes",
+            CrowdVoteStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OptionTally {
+    pub label: String,
+    pub control: Option<String>,
+    /// Viewers voting for it
+    pub votes: u32,
+    /// Sum of their vote weights, which decides the winner
+    pub weight: u64,
+}
+
+/// A channel's current or most recent vote.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrowdVote {
+    pub vote_id: Uuid,
+    pub channel: String,
+    pub title: String,
+    pub options: Vec<OptionTally>,
+    pub status: CrowdVoteStatus,
+    /// The winning option's label
+    pub winner: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl CrowdVote {
+    pub fn voters(&self) -> u32 {
+        self.options.iter().map(|o| o.votes).sum()
+    }
+}
+
+/// A vote with who voted for what.
+struct VoteState {
+    vote: CrowdVote,
+    /// Option index and weight by voter
+    ballots: HashMap<Uuid, (usize, u64)>,
+    /// Tallies changed since overlays last heard
+    dirty: bool,
+}
+
+impl VoteState {
+    /// Counts a vote, replacing the voter's earlier one.
+    fn cast(&mut self, voter: Uuid, choice: usize, weight: u64) {
+        if let Some((previous, previous_weight)) = self.ballots.insert(voter, (choice, weight)) {
+            let tally = &mut self.vote.options[previous];
+            tally.votes -= 1;
+            tally.weight -= previous_weight;
+        }
+        let tally = &mut self.vote.options[choice];
+        tally.votes += 1;
+        tally.weight += weight;
+        self.dirty = true;
+    }
+}
+
+/// The option a chat line votes for: `#N`, or an option's label.
+fn parse_choice(text: &str, options: &[OptionTally]) -> Option<usize> {
+    let text = text.trim();
+    if let Some(number) = text.strip_prefix('#') {
+        return number.parse::<usize>().ok()
+            .filter(|n| (1..=options.len()).contains(n))
+            .map(|n| n - 1);
+    }
+    options.iter().position(|o| o.label.eq_ignore_ascii_case(text))
+}
+
+/// 1, plus one per `weight_hours` watched, up to `max_weight`.
+fn vote_weight(watch_seconds: i64, weight_hours: i64, max_weight: i64) -> u64 {
+    let max_weight = max_weight.max(1) as u64;
+    if weight_hours <= 0 {
+        return 1;
+    }
+    let earned = (watch_seconds.max(0) / (weight_hours * 3600)) as u64;
+    (1 + earned).min(max_weight)
+}
+
+/// Indices of the options with the most weight; empty when nobody voted.
+fn leaders(options: &[OptionTally]) -> Vec<usize> {
+    let top = options.iter().map(|o| o.weight).max().unwrap_or(0);
+    if top == 0 {
+        return Vec::new();
+    }
+    options.iter().enumerate().filter(|(_, o)| o.weight == top).map(|(i, _)| i).collect()
+}
+
+fn normalize_channel(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+pub struct CrowdVoteService {
+    watchtime_repo: Arc<dyn WatchtimeRepository>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    /// By channel; a finished vote stays until the next one starts
+    votes: Mutex<HashMap<String, VoteState>>,
+}
+
+impl CrowdVoteService {
+    pub fn new(
+        watchtime_repo: Arc<dyn WatchtimeRepository>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            watchtime_repo,
+            event_bus,
+            settings,
+            plugin_manager,
+            votes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts chat votes, and every second pushes changed tallies and decides
+    /// votes whose window is over.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            let mut tick = tokio::time::interval(TICK);
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(BotEvent::ChatMessage { platform, channel, user, text, .. }) if platform == "twitch-irc" => {
+                            service.chat_vote(&channel, &user, &text).await;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    },
+                    _ = tick.tick() => service.tick().await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[CrowdVote] event loop stopped");
+        });
+    }
+
+    async fn chat_vote(&self, channel: &str, user: &str, text: &str) {
+        let channel = normalize_channel(channel);
+        let (choice, known_weight) = {
+            let votes = self.votes.lock();
+            let Some(state) = votes.get(&channel).filter(|s| s.vote.status == CrowdVoteStatus::Open) else { return };
+            let Some(choice) = parse_choice(text, &state.vote.options) else { return };
+            let known = Uuid::parse_str(user).ok().and_then(|id| state.ballots.get(&id)).map(|(_, w)| *w);
+            (choice, known)
+        };
+        let Ok(user_id) = Uuid::parse_str(user) else { return };
+
+        // A viewer's weight is looked up once per vote
+        let weight = match known_weight {
+            Some(weight) => weight,
+            None => self.weight_of(&channel, user_id).await,
+        };
+        let mut votes = self.votes.lock();
+        if let Some(state) = votes.get_mut(&channel).filter(|s| s.vote.status == CrowdVoteStatus::Open) {
+            state.cast(user_id, choice, weight);
+        }
+    }
+
+    /// The chatter's vote weight from their watchtime in `channel`; 1 when it
+    /// can't be looked up.
+    async fn weight_of(&self, channel: &str, user_id: Uuid) -> u64 {
+        let weight_hours = self.settings.get_i64("crowd.weight_hours").unwrap_or(10);
+        let max_weight = self.settings.get_i64("crowd.max_weight").unwrap_or(3);
+        if weight_hours <= 0 || max_weight <= 1 {
+            return 1;
+        }
+        let identity = self.plugin_manager.user_service.platform_identity_repo
+            .get_by_user_and_platform(user_id, &Platform::TwitchIRC)
+            .await;
+        let login = match identity {
+            Ok(Some(identity)) => identity.platform_username,
+            Ok(None) => return 1,
+            Err(e) => {
+                warn!("[CrowdVote] could not identify chatter {}: {:?}", user_id, e);
+                return 1;
+            }
+        };
+        match self.watchtime_repo.get_watchtime(channel, &login).await {
+            Ok(watched) => vote_weight(watched.map_or(0, |w| w.watch_seconds), weight_hours, max_weight),
+            Err(e) => {
+                warn!("[CrowdVote] could not read watchtime of {}: {:?}", login, e);
+                1
+            }
+        }
+    }
+
+    async fn tick(&self) {
+        let now = Utc::now();
+        let (changed, expired) = {
+            let mut votes = self.votes.lock();
+            let mut changed = Vec::new();
+            let mut expired = Vec::new();
+            for state in votes.values_mut().filter(|s| s.vote.status == CrowdVoteStatus::Open) {
+                if state.vote.ends_at <= now {
+                    expired.push(state.vote.channel.clone());
+                } else if state.dirty {
+                    state.dirty = false;
+                    changed.push(state.vote.clone());
+                }
+            }
+            (changed, expired)
+        };
+        for vote in changed {
+            self.broadcast("tally", &vote).await;
+        }
+        for channel in expired {
+            if let Err(e) = self.decide(&channel).await {
+                warn!("[CrowdVote] could not decide the vote in #{}: {:?}", channel, e);
+            }
+        }
+    }
+
+    /// Opens a vote in the channel, which must not have one open already.
+    pub async fn start_vote(&self, new: NewCrowdVote) -> Result<CrowdVote, Error> {
+        let title = new.title.trim().to_string();
+        if title.is_empty() {
+            return Err(Error::Parse("A vote needs a title".into()));
+        }
+        if !(MIN_SECONDS..=MAX_SECONDS).contains(&new.duration_seconds) {
+            return Err(Error::Parse(format!("A vote lasts {} to {} seconds", MIN_SECONDS, MAX_SECONDS)));
+        }
+        if new.options.len() < 2 || new.options.len() > MAX_OPTIONS {
+            return Err(Error::Parse(format!("A vote needs 2 to {} options", MAX_OPTIONS)));
+        }
+
+        let mut options: Vec<OptionTally> = Vec::with_capacity(new.options.len());
+        for option in new.options {
+            let label = option.label.trim().to_string();
+            if label.is_empty() || label.starts_with('#') {
+                return Err(Error::Parse(format!("'{}' is not a usable option label", label)));
+            }
+            if options.iter().any(|o| o.label.eq_ignore_ascii_case(&label)) {
+                return Err(Error::Parse(format!("The option '{}' is listed twice", label)));
+            }
+            let control = option.control.map(|c| c.trim().to_lowercase()).filter(|c| !c.is_empty());
+            if let Some(control) = &control {
+                self.check_control(control).await?;
+            }
+            options.push(OptionTally { label, control, votes: 0, weight: 0 });
+        }
+
+        let channel = match new.channel.as_deref().map(normalize_channel).filter(|c| !c.is_empty()) {
+            Some(channel) => channel,
+            None => broadcaster_helix(&*self.plugin_manager.credentials_repo).await
+                .map(|h| h.login)
+                .map_err(|e| Error::Platform(format!("No channel given and no broadcaster to default to: {}", e)))?,
+        };
+
+        let started_at = Utc::now();
+        let vote = CrowdVote {
+            vote_id: Uuid::new_v4(),
+            channel: channel.clone(),
+            title,
+            options,
+            status: CrowdVoteStatus::Open,
+            winner: None,
+            started_at,
+            ends_at: started_at + Duration::seconds(new.duration_seconds as i64),
+        };
+        {
+            let mut votes = self.votes.lock();
+            if votes.get(&channel).is_some_and(|s| s.vote.status == CrowdVoteStatus::Open) {
+                return Err(Error::Parse(format!("#{} already has a vote open", channel)));
+            }
+            votes.insert(channel.clone(), VoteState { vote: vote.clone(), ballots: HashMap::new(), dirty: false });
+        }
+        info!("[CrowdVote] started '{}' in #{} for {}s", vote.title, channel, new.duration_seconds);
+
+        let options: Vec<String> = vote.options.iter().enumerate()
+            .map(|(i, o)| format!("#{} {}", i + 1, o.label))
+            .collect();
+        let text = self.text(&channel, "crowd.open", &[
+            ("title", &vote.title),
+            ("options", &options.join(", ")),
+            ("seconds", &new.duration_seconds.to_string()),
+        ]);
+        self.announce(&vote, "opened", &text).await;
+        Ok(vote)
+    }
+
+    /// Only enabled bool chat controls can be toggled by a vote.
+    async fn check_control(&self, name: &str) -> Result<(), Error> {
+        let Some(repo) = &self.plugin_manager.osc_toggle_repo else {
+            return Err(Error::Platform("OSC toggles are not available".into()));
+        };
+        match repo.get_chat_control(name).await? {
+            Some(control) if !control.enabled => Err(Error::Parse(format!("The OSC control '{}' is disabled", name))),
+            Some(control) if control.parameter_type != "bool" => {
+                Err(Error::Parse(format!("The OSC control '{}' is not an on/off toggle", name)))
+            }
+            Some(_) => Ok(()),
+            None => Err(Error::NotFound(format!("No OSC chat control named '{}'", name))),
+        }
+    }
+
+    /// Ends the channel's vote now instead of when its window is over.
+    pub async fn end_vote(&self, channel: Option<&str>) -> Result<CrowdVote, Error> {
+        let channel = self.resolve_channel(channel).await?;
+        self.decide(&channel).await
+    }
+
+    /// Ends the channel's vote without a winner.
+    pub async fn cancel_vote(&self, channel: Option<&str>) -> Result<CrowdVote, Error> {
+        let channel = self.resolve_channel(channel).await?;
+        let vote = {
+            let mut votes = self.votes.lock();
+            let state = Self::open_state(&mut votes, &channel)?;
+            state.vote.status = CrowdVoteStatus::Cancelled;
+            state.vote.clone()
+        };
+        info!("[CrowdVote] cancelled '{}' in #{}", vote.title, channel);
+        let text = self.text(&channel, "crowd.cancelled", &[("title", &vote.title)]);
+        self.announce(&vote, "cancelled", &text).await;
+        Ok(vote)
+    }
+
+    /// The channel's open vote, or the last one it had.
+    pub async fn get_vote(&self, channel: Option<&str>) -> Result<CrowdVote, Error> {
+        let channel = self.resolve_channel(channel).await?;
+        self.votes.lock().get(&channel)
+            .map(|s| s.vote.clone())
+            .ok_or_else(|| Error::NotFound(format!("#{} has had no vote yet", channel)))
+    }
+
+    async fn resolve_channel(&self, channel: Option<&str>) -> Result<String, Error> {
+        match channel.map(normalize_channel).filter(|c| !c.is_empty()) {
+            Some(channel) => Ok(channel),
+            None => Ok(broadcaster_helix(&*self.plugin_manager.credentials_repo).await?.login),
+        }
+    }
+
+    fn open_state<'a>(votes: &'a mut HashMap<String, VoteState>, channel: &str) -> Result<&'a mut VoteState, Error> {
+        votes.get_mut(channel)
+            .filter(|s| s.vote.status == CrowdVoteStatus::Open)
+            .ok_or_else(|| Error::NotFound(format!("#{} has no open vote", channel)))
+    }
+
+    /// Closes the vote, picking the heaviest option (ties at random), toggles
+    /// the winner's control and tells pipelines.
+    async fn decide(&self, channel: &str) -> Result<CrowdVote, Error> {
+        let (vote, winner) = {
+            let mut votes = self.votes.lock();
+            let state = Self::open_state(&mut votes, channel)?;
+            let leaders = leaders(&state.vote.options);
+            let winner = match leaders.len() {
+                0 => None,
+                1 => Some(leaders[0]),
+                n => Some(leaders[rand::rng().random_range(0..n)]),
+            };
+            state.vote.status = if winner.is_some() { CrowdVoteStatus::Decided } else { CrowdVoteStatus::NoVotes };
+            state.vote.winner = winner.map(|i| state.vote.options[i].label.clone());
+            state.vote.ends_at = state.vote.ends_at.min(Utc::now());
+            (state.vote.clone(), winner.map(|i| state.vote.options[i].clone()))
+        };
+
+        let Some(winner) = winner else {
+            info!("[CrowdVote] nobody voted in '{}' in #{}", vote.title, channel);
+            let text = self.text(channel, "crowd.no_votes", &[("title", &vote.title)]);
+            self.announce(&vote, "decided", &text).await;
+            return Ok(vote);
+        };
+        info!("[CrowdVote] '{}' won '{}' in #{} with weight {}", winner.label, vote.title, channel, winner.weight);
+
+        if let Some(control) = &winner.control {
+            match &self.plugin_manager.osc_toggle_service {
+                Some(osc) => {
+                    if let Err(refusal) = osc.chat_toggle(control, &["broadcaster".to_string()]).await {
+                        warn!("[CrowdVote] could not toggle {}: {:?}", control, refusal);
+                    }
+                }
+                None => debug!("[CrowdVote] no OSC toggle service to toggle {}", control),
+            }
+        }
+
+        let text = self.text(channel, "crowd.winner", &[("winner", &winner.label), ("title", &vote.title)]);
+        self.announce(&vote, "decided", &text).await;
+        self.event_bus.publish(BotEvent::CrowdVoteDecided {
+            channel: vote.channel.clone(),
+            title: vote.title.clone(),
+            winner: winner.label,
+            control: winner.control,
+            weight: winner.weight,
+            voters: vote.voters(),
+            timestamp: Utc::now(),
+        }).await;
+        Ok(vote)
+    }
+
+    /// Chat text in the channel's language.
+    fn text(&self, channel: &str, key: &str, args: &[(&str, &str)]) -> String {
+        i18n::text_for(self.plugin_manager.localizer.as_deref(), "twitch-irc", channel, key, args)
+    }
+
+    /// Posts to chat if `crowd.announce_chat` is on, and tells overlays.
+    async fn announce(&self, vote: &CrowdVote, event: &str, text: &str) {
+        if self.settings.get_bool("crowd.announce_chat").unwrap_or(true) {
+            let sender = MessageSender::new(
+                self.plugin_manager.credentials_repo.clone(),
+                self.plugin_manager.platform_manager.clone(),
+            );
+            if let Err(e) = sender.send_twitch_message(&vote.channel, text, None, Uuid::nil()).await {
+                warn!("[CrowdVote] could not send to #{}: {:?}", vote.channel, e);
+            }
+        }
+        self.broadcast(event, vote).await;
+    }
+
+    async fn broadcast(&self, event: &str, vote: &CrowdVote) {
+        let payload = json!({ "event": event, "vote": vote });
+        self.plugin_manager.broadcast(
+            PluginStreamResponse {
+                payload: Some(RespPayload::GameEvent(GameEvent {
+                    name: "crowd_vote".to_string(),
+                    json: payload.to_string(),
+                })),
+            },
+            None,
+        ).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(labels: &[&str]) -> VoteState {
+        let now = Utc::now();
+        VoteState {
+            vote: CrowdVote {
+                vote_id: Uuid::new_v4(),
+                channel: "test".into(),
+                title: "Next effect".into(),
+                options: labels.iter()
+                    .map(|l| OptionTally { label: l.to_string(), control: None, votes: 0, weight: 0 })
+                    .collect(),
+                status: CrowdVoteStatus::Open,
+                winner: None,
+                started_at: now,
+                ends_at: now,
+            },
+            ballots: HashMap::new(),
+            dirty: false,
+        }
+    }
+
+    #[test]
+    fn test_votes_parse_weigh_and_can_be_changed() {
+        let mut vote = state(&["Confetti", "Spin", "Ears"]);
+        assert_eq!(parse_choice("#2", &vote.vote.options), Some(1));
+        assert_eq!(parse_choice("  ears ", &vote.vote.options), Some(2));
+        assert_eq!(parse_choice("#4", &vote.vote.options), None);
+        assert_eq!(parse_choice("#0", &vote.vote.options), None);
+        assert_eq!(parse_choice("confetti please", &vote.vote.options), None);
+
+        assert_eq!(vote_weight(0, 10, 3), 1);
+        assert_eq!(vote_weight(25 * 3600, 10, 3), 3);
+        assert_eq!(vote_weight(500 * 3600, 10, 3), 3);
+        assert_eq!(vote_weight(500 * 3600, 0, 3), 1);
+
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        vote.cast(a, 0, 3);
+        vote.cast(b, 1, 1);
+        assert_eq!(leaders(&vote.vote.options), vec![0]);
+        vote.cast(a, 1, 3);
+        assert_eq!(vote.vote.options[0].votes, 0);
+        assert_eq!(vote.vote.options[1].weight, 4);
+        assert_eq!(vote.vote.voters(), 2);
+        assert_eq!(leaders(&vote.vote.options), vec![1]);
+
+        assert!(leaders(&state(&["A", "B"]).vote.options).is_empty());
+    }
+}
@@ -1,3 +1,8 @@
+use uuid::Uuid;
+use maowbot_common::models::user_notes::{ModerationAction, ModerationActionType};
+use maowbot_common::traits::repository_traits::UserNotesRepository;
+use crate::platforms::twitch_eventsub::events::{ChannelBan, ChannelUnban};
+use crate::services::user_service::UserService;
 use crate::Error;
 
 pub async fn handle_moderation_action() -> Result<(), Error> {
@@ -14,3 +19,86 @@ pub async fn handle_moderator_remove() -> Result<(), Error> {
     // channel.moderator.remove
     Ok(())
 }
+
+/// "channel.ban" covers both bans and timeouts; records it in the user's moderation history.
+pub async fn handle_channel_ban(
+    evt: ChannelBan,
+    user_service: &UserService,
+    notes_repo: &dyn UserNotesRepository,
+) -> Result<(), Error> {
+    let user = user_service
+        .get_or_create_user("twitch-eventsub", &evt.user_id, Some(&evt.user_name))
+        .await?;
+    notes_repo.record_action(&ban_action(&evt, user.user_id)).await
+}
+
+/// The history entry for a ban: a timeout when it ends, with its length.
+fn ban_action(evt: &ChannelBan, user_id: Uuid) -> ModerationAction {
+    let action_type = if evt.is_permanent { ModerationActionType::Ban } else { ModerationActionType::Timeout };
+    let mut action = ModerationAction::new(
+        user_id,
+        "twitch",
+        &evt.broadcaster_user_login,
+        action_type,
+        &evt.moderator_user_login,
+    );
+    action.reason = Some(evt.reason.clone()).filter(|r| !r.trim().is_empty());
+    action.duration_seconds = evt.ends_at.map(|end| (end - evt.banned_at).num_seconds() as i32);
+    action.created_at = evt.banned_at;
+    action
+}
+
+/// "channel.unban"
+pub async fn handle_channel_unban(
+    evt: ChannelUnban,
+    user_service: &UserService,
+    notes_repo: &dyn UserNotesRepository,
+) -> Result<(), Error> {
+    let user = user_service
+        .get_or_create_user("twitch-eventsub", &evt.user_id, Some(&evt.user_name))
+        .await?;
+
+    let action = ModerationAction::new(
+        user.user_id,
+        "twitch",
+        &evt.broadcaster_user_login,
+        ModerationActionType::Unban,
+        &evt.moderator_user_login,
+    );
+    notes_repo.record_action(&action).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ban(ends_at: Option<&str>, reason: &str) -> ChannelBan {
+        serde_json::from_value(json!({
+            "user_id": "1234", "user_login": "spammer", "user_name": "Spammer",
+            "broadcaster_user_id": "1", "broadcaster_user_login": "maowcaster", "broadcaster_user_name": "MaowCaster",
+            "moderator_user_id": "2", "moderator_user_login": "modcat", "moderator_user_name": "ModCat",
+            "reason": reason,
+            "banned_at": "2026-10-16T12:00:00Z",
+            "ends_at": ends_at,
+            "is_permanent": ends_at.is_none(),
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_timeouts_keep_their_length_and_bans_have_none() {
+        let user_id = Uuid::new_v4();
+        let timeout = ban_action(&ban(Some("2026-10-16T12:10:00Z"), "caps"), user_id);
+        assert_eq!(timeout.action_type, ModerationActionType::Timeout);
+        assert_eq!(timeout.duration_seconds, Some(600));
+        assert_eq!(timeout.reason.as_deref(), Some("caps"));
+        assert_eq!((timeout.channel.as_str(), timeout.moderator.as_str()), ("maowcaster", "modcat"));
+        assert_eq!(timeout.user_id, user_id);
+
+        let permanent = ban_action(&ban(None, "  "), user_id);
+        assert_eq!(permanent.action_type, ModerationActionType::Ban);
+        assert_eq!(permanent.duration_seconds, None);
+        assert_eq!(permanent.reason, None);
+        assert_eq!(permanent.created_at.to_rfc3339(), "2026-10-16T12:00:00+00:00");
+    }
+}
@@ -26,7 +26,7 @@ pub async fn handle_custom_reward_redemption_add(
             id: evt.reward.id.clone(),
             title: evt.reward.title.clone(),
             prompt: evt.reward.prompt.clone(),
-            cost: evt.reward.cost as u64,
+            cost: evt.reward.cost,
         },
     };
 
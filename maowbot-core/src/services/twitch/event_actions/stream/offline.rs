@@ -1,5 +1,5 @@
 // File: maowbot-core/src/services/twitch/event_actions/stream/offline.rs
-use maowbot_common::traits::repository_traits::BotConfigRepository;
+use maowbot_common::traits::repository_traits::{BotConfigRepository, DiscordRepository};
 use maowbot_common::models::platform::Platform;
 use maowbot_common::models::discord::{DiscordEmbed, DiscordColor};
 use crate::Error;
@@ -8,14 +8,13 @@ use crate::services::user_service::UserService;
 use crate::services::twitch::redeem_service::RedeemService;
 use crate::platforms::manager::PlatformManager;
 use crate::tasks::redeem_sync;
-use crate::repositories::postgres::discord::PostgresDiscordRepository;
 pub async fn handle_stream_offline(
-    evt: StreamOffline,
+    _evt: StreamOffline,
     redeem_service: &RedeemService,
     platform_manager: &PlatformManager,
     user_service: &UserService,
     bot_config_repo: &dyn BotConfigRepository,
-    discord_repo: &PostgresDiscordRepository,
+    discord_repo: &dyn DiscordRepository,
 ) -> Result<(), Error> {
     // 1) Possibly do your custom logic with 'evt'
     // e.g. store something in DB, or log a message
@@ -45,7 +44,7 @@ pub async fn handle_stream_offline(
             // Create the embed for the offline announcement
             let mut embed = DiscordEmbed::new();
             embed.title = Some(format!("{} has ended their stream", twitch_name));
-            embed.description = Some(format!("Thanks for watching! See you next time."));
+            embed.description = Some("Thanks for watching! See you next time.".to_string());
             embed.color = Some(DiscordColor::RED);
             embed.timestamp = Some(chrono::Utc::now());
 
@@ -1,7 +1,7 @@
 // ========================================================
 // File: maowbot-core/src/services/twitch/event_actions/stream/online.rs
 // ========================================================
-use maowbot_common::traits::repository_traits::BotConfigRepository;
+use maowbot_common::traits::repository_traits::{BotConfigRepository, DiscordRepository};
 use maowbot_common::models::platform::Platform;
 use maowbot_common::models::discord::{DiscordEmbed, DiscordEmbedAuthor, DiscordEmbedThumbnail, DiscordColor, DiscordEmbedField};
 use crate::Error;
@@ -9,11 +9,9 @@ use crate::platforms::twitch_eventsub::events::StreamOnline;
 use crate::services::user_service::UserService;
 use crate::services::twitch::redeem_service::RedeemService;
 use crate::platforms::manager::PlatformManager;
-use crate::repositories::postgres::discord::PostgresDiscordRepository;
 use crate::tasks::redeem_sync;
 use tracing::{debug, info};
 // Import the updated fetch_stream_details function and TwitchHelixClient.
-use crate::platforms::twitch::client::TwitchHelixClient;
 use crate::platforms::twitch::requests::stream::fetch_stream_details;
 /// Handles the Twitch stream.online event by fetching real-time stream details.
 pub async fn handle_stream_online(
@@ -22,7 +20,7 @@ pub async fn handle_stream_online(
     platform_manager: &PlatformManager,
     user_service: &UserService,
     bot_config_repo: &dyn BotConfigRepository,
-    discord_repo: &PostgresDiscordRepository,
+    discord_repo: &dyn DiscordRepository,
 ) -> Result<(), Error> {
     debug!("Entered handle_stream_online with event: {:?}", evt);
     // 1) Retrieve the broadcaster credential for Twitch.
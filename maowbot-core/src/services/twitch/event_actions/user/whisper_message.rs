@@ -1,6 +1,17 @@
+use tracing::debug;
+use crate::platforms::twitch_eventsub::events::UserWhisperMessage;
+use crate::services::user_service::UserService;
 use crate::Error;
 
-/// user.whisper.message
-pub async fn handle_whisper_message() -> Result<(), Error> {
+/// user.whisper.message: makes sure the sender is a known user. Pipelines see
+/// the event on the bus and can answer with a private reply.
+pub async fn handle_whisper_message(
+    evt: UserWhisperMessage,
+    user_service: &UserService,
+) -> Result<(), Error> {
+    user_service
+        .get_or_create_user("twitch-eventsub", &evt.from_user_id, Some(&evt.from_user_login))
+        .await?;
+    debug!("Whisper from {} to {}", evt.from_user_login, evt.to_user_login);
     Ok(())
-}
\ No newline at end of file
+}
@@ -2,18 +2,19 @@
 
 use std::sync::Arc;
 use tracing::{debug, error, info};
-use maowbot_common::traits::repository_traits::BotConfigRepository;
+use maowbot_common::traits::repository_traits::{BotConfigRepository, DiscordRepository, UserNotesRepository};
 use crate::eventbus::{EventBus, BotEvent, TwitchEventSubData};
 use crate::platforms::manager::PlatformManager;
 use crate::services::RedeemService;
 use crate::services::user_service::UserService;
 
-use crate::repositories::postgres::discord::PostgresDiscordRepository;
 use super::event_actions::{
     channel::update as channel_update_actions,
     stream::online as stream_online_actions,
     stream::offline as stream_offline_actions,
     channel::points as channel_points_actions,
+    channel::moderate as moderate_actions,
+    user::whisper_message as whisper_actions,
 };
 
 /// The EventSubService will subscribe to the EventBus, look for `BotEvent::TwitchEventSub`,
@@ -27,18 +28,21 @@ pub struct EventSubService {
     pub bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
 
     /// NEW: Reference to the Discord repository, so we can pass it to `handle_stream_online/offline`.
-    pub discord_repo: Arc<PostgresDiscordRepository>,
+    pub discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
+
+    /// Bans, timeouts and unbans are recorded in the user's moderation history.
+    pub user_notes_repo: Arc<dyn UserNotesRepository>,
 }
 
 impl EventSubService {
-    /// Adjust `new` to receive an `Arc<PostgresDiscordRepository>` and store it.
     pub fn new(
         event_bus: Arc<EventBus>,
         redeem_service: Arc<RedeemService>,
         user_service: Arc<UserService>,
         platform_manager: Arc<PlatformManager>,
         bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
-        discord_repo: Arc<PostgresDiscordRepository>, // <--- new param
+        discord_repo: Arc<dyn DiscordRepository + Send + Sync>, // <--- new param
+        user_notes_repo: Arc<dyn UserNotesRepository>,
     ) -> Self {
         Self {
             event_bus,
@@ -47,6 +51,7 @@ impl EventSubService {
             platform_manager,
             bot_config_repo,
             discord_repo, // store it
+            user_notes_repo,
         }
     }
 
@@ -70,9 +75,9 @@ impl EventSubService {
                             // Pass the additional &self.discord_repo to match the 6-arg function
                             if let Err(e) = stream_online_actions::handle_stream_online(
                                 ev,
-                                &*self.redeem_service,
-                                &*self.platform_manager,
-                                &*self.user_service,
+                                &self.redeem_service,
+                                &self.platform_manager,
+                                &self.user_service,
                                 &*self.bot_config_repo,
                                 &*self.discord_repo,
                             ).await {
@@ -83,9 +88,9 @@ impl EventSubService {
                             // Same fix for the offline event
                             if let Err(e) = stream_offline_actions::handle_stream_offline(
                                 ev,
-                                &*self.redeem_service,
-                                &*self.platform_manager,
-                                &*self.user_service,
+                                &self.redeem_service,
+                                &self.platform_manager,
+                                &self.user_service,
                                 &*self.bot_config_repo,
                                 &*self.discord_repo,
                             ).await {
@@ -97,8 +102,8 @@ impl EventSubService {
                         TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(ev) => {
                             if let Err(e) = channel_points_actions::handle_custom_reward_redemption_add(
                                 ev,
-                                &*self.redeem_service,
-                                &*self.user_service
+                                &self.redeem_service,
+                                &self.user_service
                             ).await
                             {
                                 error!("Error handling custom_reward_redemption.add: {:?}", e);
@@ -106,6 +111,33 @@ impl EventSubService {
                         }
                         // ------------------------------------------------------------------------
 
+                        TwitchEventSubData::ChannelBan(ev) => {
+                            if let Err(e) = moderate_actions::handle_channel_ban(
+                                ev,
+                                &self.user_service,
+                                &*self.user_notes_repo,
+                            ).await {
+                                error!("Error recording channel.ban: {:?}", e);
+                            }
+                        }
+                        TwitchEventSubData::ChannelUnban(ev) => {
+                            if let Err(e) = moderate_actions::handle_channel_unban(
+                                ev,
+                                &self.user_service,
+                                &*self.user_notes_repo,
+                            ).await {
+                                error!("Error recording channel.unban: {:?}", e);
+                            }
+                        }
+                        TwitchEventSubData::UserWhisperMessage(ev) => {
+                            if let Err(e) = whisper_actions::handle_whisper_message(
+                                ev,
+                                &self.user_service,
+                            ).await {
+                                error!("Error handling user.whisper.message: {:?}", e);
+                            }
+                        }
+
                         // If not matched, log "ignoring unhandled variant"
                         _ => {
                             debug!(
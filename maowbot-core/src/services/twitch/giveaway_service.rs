@@ -0,0 +1,535 @@
+// File: maowbot-core/src/services/twitch/giveaway_service.rs
+//
+// Giveaways on the broadcaster's Twitch channel. Viewers enter by typing the
+// keyword in chat or by redeeming a channel point reward the service creates;
+// eligibility (sub-only, follow age, recent winners) is checked on entry.
+// Giveaways and entries live in the database, so a restart keeps them.
+// Openings, closings and winners are announced in chat, on Discord and to
+// plugins as a `giveaway` GameEvent for overlays.
+
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use parking_lot::Mutex;
+use rand::Rng;
+use serde_json::json;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::giveaway::{
+    pick_weighted, total_weight, Giveaway, GiveawayEntry, GiveawayRules, GiveawayStatus,
+};
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::GiveawayRepository;
+use maowbot_proto::plugs::{
+    plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse,
+};
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData};
+use crate::platforms::twitch::requests::channel_points::CustomRewardBody;
+use crate::plugins::manager::PluginManager;
+use crate::services::message_sender::MessageSender;
+use crate::services::twitch::{broadcaster_helix, BroadcasterHelix};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use crate::i18n;
+
+/// Twitch rejects longer reward titles.
+const MAX_REWARD_TITLE: usize = 45;
+
+/// What's needed to open a giveaway.
+#[derive(Debug, Clone)]
+pub struct NewGiveaway {
+    pub title: String,
+    /// Defaults to the broadcaster's channel
+    pub channel: Option<String>,
+    pub keyword: Option<String>,
+    pub points_cost: i64,
+    pub rules: GiveawayRules,
+}
+
+/// Someone trying to enter, from chat or a redeem.
+struct Entrant {
+    user_id: Uuid,
+    twitch_user_id: String,
+    username: String,
+}
+
+fn normalize_channel(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+pub struct GiveawayService {
+    repo: Arc<dyn GiveawayRepository>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    /// Open giveaways, mirrored from the database
+    open: Mutex<Vec<Giveaway>>,
+}
+
+impl GiveawayService {
+    pub fn new(
+        repo: Arc<dyn GiveawayRepository>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            repo,
+            event_bus,
+            settings,
+            plugin_manager,
+            open: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Reloads giveaways that were open before a restart, then listens for
+    /// chat keywords and reward redemptions.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            match service.repo.list_open_giveaways().await {
+                Ok(open) => {
+                    if !open.is_empty() {
+                        info!("[Giveaways] resuming {} open giveaway(s)", open.len());
+                    }
+                    *service.open.lock() = open;
+                }
+                Err(e) => warn!("[Giveaways] could not load open giveaways: {:?}", e),
+            }
+
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Giveaways] event loop stopped");
+        });
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        match event {
+            BotEvent::ChatMessage { platform, channel, user, text, .. } if platform == "twitch-irc" => {
+                let channel = normalize_channel(&channel);
+                let text = text.trim();
+                let giveaway = self.open.lock().iter()
+                    .find(|g| g.channel == channel
+                        && g.keyword.as_deref().is_some_and(|k| k.eq_ignore_ascii_case(text)))
+                    .cloned();
+                let (Some(giveaway), Ok(user_id)) = (giveaway, Uuid::parse_str(&user)) else { return };
+                match self.chat_entrant(user_id).await {
+                    Ok(entrant) => {
+                        if let Err(reason) = self.enter(&giveaway, &entrant).await {
+                            let text = self.text(&giveaway.channel, "giveaway.rejected", &[
+                                ("user", &entrant.username),
+                                ("reason", &reason),
+                            ]);
+                            self.say(&giveaway.channel, &text).await;
+                        }
+                    }
+                    Err(e) => warn!("[Giveaways] could not identify chatter {}: {:?}", user_id, e),
+                }
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(evt)) => {
+                let giveaway = self.open.lock().iter()
+                    .find(|g| g.reward_id.as_deref() == Some(evt.reward.id.as_str()))
+                    .cloned();
+                let Some(giveaway) = giveaway else { return };
+
+                let user = match self.plugin_manager.user_service
+                    .get_or_create_user("twitch-irc", &evt.user_id, Some(&evt.user_login))
+                    .await
+                {
+                    Ok(user) => user,
+                    Err(e) => {
+                        warn!("[Giveaways] could not resolve redeemer {}: {:?}", evt.user_login, e);
+                        return;
+                    }
+                };
+                let entrant = Entrant {
+                    user_id: user.user_id,
+                    twitch_user_id: evt.user_id.clone(),
+                    username: evt.user_name.clone(),
+                };
+                // Rejected redeems are cancelled so Twitch refunds the points
+                let status = match self.enter(&giveaway, &entrant).await {
+                    Ok(()) => "FULFILLED",
+                    Err(reason) => {
+                        let text = self.text(&giveaway.channel, "giveaway.rejected_refunded", &[
+                            ("user", &entrant.username),
+                            ("reason", &reason),
+                        ]);
+                        self.say(&giveaway.channel, &text).await;
+                        "CANCELED"
+                    }
+                };
+                if let Err(e) = self.set_redemption_status(&evt.reward.id, &evt.id, status).await {
+                    warn!("[Giveaways] could not mark redemption {} {}: {:?}", evt.id, status, e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn chat_entrant(&self, user_id: Uuid) -> Result<Entrant, Error> {
+        let identity = self.plugin_manager.user_service.platform_identity_repo
+            .get_by_user_and_platform(user_id, &Platform::TwitchIRC)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("No Twitch identity for user {}", user_id)))?;
+        Ok(Entrant {
+            user_id,
+            username: identity.platform_display_name.clone().unwrap_or(identity.platform_username.clone()),
+            twitch_user_id: identity.platform_user_id,
+        })
+    }
+
+    /// Records an entry, or returns why the entrant can't enter.
+    async fn enter(&self, giveaway: &Giveaway, entrant: &Entrant) -> Result<(), String> {
+        let rules = &giveaway.rules;
+        let tr = |key: &str, args: &[(&str, &str)]| self.text(&giveaway.channel, key, args);
+        let unverifiable = |what: &str, e: Error| {
+            warn!("[Giveaways] could not check {} for {}: {:?}", what, entrant.username, e);
+            tr("giveaway.unverifiable", &[])
+        };
+
+        if rules.exclude_winners_days > 0 {
+            let since = Utc::now() - Duration::days(rules.exclude_winners_days as i64);
+            if self.repo.has_won_since(entrant.user_id, since).await.map_err(|e| unverifiable("past wins", e))? {
+                return Err(tr("giveaway.recent_winner", &[("days", &rules.exclude_winners_days.to_string())]));
+            }
+        }
+
+        let mut is_subscriber = false;
+        if rules.sub_only || rules.sub_weight > 1 || rules.min_follow_days > 0 {
+            let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await
+                .map_err(|e| unverifiable("eligibility", e))?;
+            if rules.sub_only || rules.sub_weight > 1 {
+                is_subscriber = match helix.client.fetch_subscription(&helix.broadcaster_id, &entrant.twitch_user_id).await {
+                    Ok(sub) => sub.is_some(),
+                    Err(e) if rules.sub_only => return Err(unverifiable("subscription", e)),
+                    Err(_) => false,
+                };
+                if rules.sub_only && !is_subscriber {
+                    return Err(tr("giveaway.subs_only", &[]));
+                }
+            }
+            if rules.min_follow_days > 0 && entrant.twitch_user_id != helix.broadcaster_id {
+                let followed_at = helix.client.fetch_follow_date(&entrant.twitch_user_id, &helix.broadcaster_id).await
+                    .map_err(|e| unverifiable("follow age", e))?;
+                let old_enough = followed_at
+                    .is_some_and(|at| Utc::now() - at >= Duration::days(rules.min_follow_days as i64));
+                if !old_enough {
+                    return Err(tr("giveaway.follow_age", &[("days", &rules.min_follow_days.to_string())]));
+                }
+            }
+        }
+
+        let entry = GiveawayEntry {
+            entry_id: Uuid::new_v4(),
+            giveaway_id: giveaway.giveaway_id,
+            user_id: entrant.user_id,
+            username: entrant.username.clone(),
+            is_subscriber,
+            weight: if is_subscriber { rules.sub_weight.max(1) } else { 1 },
+            is_winner: false,
+            rerolled: false,
+            entered_at: Utc::now(),
+            won_at: None,
+        };
+        match self.repo.add_entry(&entry).await {
+            Ok(true) => {
+                debug!("[Giveaways] {} entered '{}' (weight {})", entry.username, giveaway.title, entry.weight);
+                Ok(())
+            }
+            Ok(false) => Err(tr("giveaway.already_entered", &[])),
+            Err(e) => Err(unverifiable("entry", e)),
+        }
+    }
+
+    /// Opens a giveaway, creating its channel point reward when it costs points.
+    pub async fn open_giveaway(&self, new: NewGiveaway) -> Result<Giveaway, Error> {
+        let title = new.title.trim().to_string();
+        if title.is_empty() {
+            return Err(Error::Parse("A giveaway needs a title".into()));
+        }
+        let keyword = new.keyword.map(|k| k.trim().to_string()).filter(|k| !k.is_empty());
+        if keyword.is_none() && new.points_cost <= 0 {
+            return Err(Error::Parse("A giveaway needs an entry keyword or a points cost".into()));
+        }
+        if new.rules.sub_weight < 1 || new.rules.min_follow_days < 0 || new.rules.exclude_winners_days < 0 {
+            return Err(Error::Parse("sub_weight must be at least 1 and day limits can't be negative".into()));
+        }
+
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await;
+        let channel = match new.channel.as_deref().map(normalize_channel).filter(|c| !c.is_empty()) {
+            Some(channel) => channel,
+            None => helix.as_ref().map(|h| h.login.clone()).map_err(|e| {
+                Error::Platform(format!("No channel given and no broadcaster to default to: {}", e))
+            })?,
+        };
+        if let Some(keyword) = &keyword {
+            let taken = self.open.lock().iter()
+                .any(|g| g.channel == channel && g.keyword.as_deref().is_some_and(|k| k.eq_ignore_ascii_case(keyword)));
+            if taken {
+                return Err(Error::Parse(format!("Another open giveaway in #{} already uses '{}'", channel, keyword)));
+            }
+        }
+
+        let reward_id = if new.points_cost > 0 {
+            let BroadcasterHelix { broadcaster_id, client, .. } = helix?;
+            let reward_title: String = format!("Giveaway: {}", title).chars().take(MAX_REWARD_TITLE).collect();
+            let reward = client.create_custom_reward(&broadcaster_id, &CustomRewardBody {
+                title: Some(reward_title),
+                cost: Some(new.points_cost as u64),
+                prompt: Some(format!("Enter the \"{}\" giveaway", title)),
+                is_enabled: Some(true),
+                is_max_per_user_per_stream_enabled: Some(true),
+                max_per_user_per_stream: Some(1),
+                ..Default::default()
+            }).await?;
+            Some(reward.id)
+        } else {
+            None
+        };
+
+        let giveaway = Giveaway {
+            giveaway_id: Uuid::new_v4(),
+            title,
+            channel,
+            keyword,
+            points_cost: new.points_cost.max(0),
+            reward_id,
+            rules: new.rules,
+            status: GiveawayStatus::Open,
+            created_at: Utc::now(),
+            closed_at: None,
+        };
+        if let Err(e) = self.repo.create_giveaway(&giveaway).await {
+            self.delete_reward(&giveaway).await;
+            return Err(e);
+        }
+        self.open.lock().push(giveaway.clone());
+        info!("[Giveaways] opened '{}' in #{}", giveaway.title, giveaway.channel);
+
+        let tr = |key: &str, args: &[(&str, &str)]| self.text(&giveaway.channel, key, args);
+        let cost = giveaway.points_cost.to_string();
+        let how = match (&giveaway.keyword, giveaway.points_cost) {
+            (Some(k), 0) => tr("giveaway.how.keyword", &[("keyword", k)]),
+            (Some(k), _) => tr("giveaway.how.keyword_or_points", &[("keyword", k), ("cost", &cost)]),
+            (None, _) => tr("giveaway.how.points", &[("cost", &cost)]),
+        };
+        let mut text = tr("giveaway.open", &[("title", &giveaway.title), ("how", &how)]);
+        if giveaway.rules.sub_only {
+            text.push(' ');
+            text.push_str(&tr("giveaway.sub_only", &[]));
+        } else if giveaway.rules.sub_weight > 1 {
+            text.push(' ');
+            text.push_str(&tr("giveaway.sub_weight", &[("weight", &giveaway.rules.sub_weight.to_string())]));
+        }
+        self.announce(&giveaway, "opened", &text, json!({})).await;
+        Ok(giveaway)
+    }
+
+    /// Stops taking entries.
+    pub async fn close_giveaway(&self, giveaway_id: Uuid) -> Result<Giveaway, Error> {
+        let giveaway = self.finish(giveaway_id, GiveawayStatus::Closed).await?;
+        let entries = self.repo.list_entries(giveaway_id).await?.len();
+        let key = if entries == 1 { "giveaway.closed_one" } else { "giveaway.closed" };
+        let text = self.text(&giveaway.channel, key, &[("title", &giveaway.title), ("count", &entries.to_string())]);
+        self.announce(&giveaway, "closed", &text, json!({ "entry_count": entries })).await;
+        Ok(giveaway)
+    }
+
+    /// Closes the giveaway without drawing. Points spent on entries are not refunded.
+    pub async fn cancel_giveaway(&self, giveaway_id: Uuid) -> Result<Giveaway, Error> {
+        let giveaway = self.finish(giveaway_id, GiveawayStatus::Cancelled).await?;
+        let text = self.text(&giveaway.channel, "giveaway.cancelled", &[("title", &giveaway.title)]);
+        self.announce(&giveaway, "cancelled", &text, json!({})).await;
+        Ok(giveaway)
+    }
+
+    async fn finish(&self, giveaway_id: Uuid, status: GiveawayStatus) -> Result<Giveaway, Error> {
+        let mut giveaway = self.get_giveaway(giveaway_id).await?;
+        match (giveaway.status, status) {
+            (GiveawayStatus::Cancelled, _) => {
+                return Err(Error::Parse(format!("Giveaway \"{}\" was cancelled", giveaway.title)));
+            }
+            (GiveawayStatus::Closed, GiveawayStatus::Closed) => return Ok(giveaway),
+            _ => {}
+        }
+        self.delete_reward(&giveaway).await;
+        giveaway.status = status;
+        giveaway.reward_id = None;
+        giveaway.closed_at.get_or_insert_with(Utc::now);
+        self.repo.update_giveaway(&giveaway).await?;
+        self.open.lock().retain(|g| g.giveaway_id != giveaway_id);
+        info!("[Giveaways] '{}' is now {}", giveaway.title, status);
+        Ok(giveaway)
+    }
+
+    /// Draws up to `count` winners, closing the giveaway first if it's still open.
+    pub async fn draw(&self, giveaway_id: Uuid, count: usize) -> Result<Vec<GiveawayEntry>, Error> {
+        let mut giveaway = self.get_giveaway(giveaway_id).await?;
+        if giveaway.status == GiveawayStatus::Open {
+            giveaway = self.close_giveaway(giveaway_id).await?;
+        } else if giveaway.status == GiveawayStatus::Cancelled {
+            return Err(Error::Parse(format!("Giveaway \"{}\" was cancelled", giveaway.title)));
+        }
+
+        let mut entries = self.repo.list_entries(giveaway_id).await?;
+        let mut winners = Vec::new();
+        for _ in 0..count.max(1) {
+            let Some(winner) = self.pick(&mut entries).await? else { break };
+            winners.push(winner);
+        }
+        if winners.is_empty() {
+            return Err(Error::NotFound(format!("Nobody is left to draw in \"{}\"", giveaway.title)));
+        }
+
+        let names: Vec<&str> = winners.iter().map(|w| w.username.as_str()).collect();
+        let text = self.text(&giveaway.channel, "giveaway.winners", &[("winners", &names.join(", ")), ("title", &giveaway.title)]);
+        self.announce(&giveaway, "winner", &text, json!({ "winners": winners })).await;
+        Ok(winners)
+    }
+
+    /// Replaces a winner (`username`, or the latest winner) with a fresh draw.
+    pub async fn reroll(&self, giveaway_id: Uuid, username: Option<&str>) -> Result<GiveawayEntry, Error> {
+        let giveaway = self.get_giveaway(giveaway_id).await?;
+        if giveaway.status == GiveawayStatus::Cancelled {
+            return Err(Error::Parse(format!("Giveaway \"{}\" was cancelled", giveaway.title)));
+        }
+        let mut entries = self.repo.list_entries(giveaway_id).await?;
+
+        let previous = entries.iter_mut()
+            .filter(|e| e.is_winner)
+            .filter(|e| username.is_none_or(|u| e.username.eq_ignore_ascii_case(u.trim_start_matches('@'))))
+            .max_by_key(|e| e.won_at)
+            .ok_or_else(|| Error::NotFound(match username {
+                Some(u) => format!("{} is not a winner of \"{}\"", u, giveaway.title),
+                None => format!("\"{}\" has no winner to re-roll", giveaway.title),
+            }))?;
+        previous.is_winner = false;
+        previous.rerolled = true;
+        self.repo.update_entry(previous).await?;
+        let previous_name = previous.username.clone();
+
+        let winner = self.pick(&mut entries).await?
+            .ok_or_else(|| Error::NotFound(format!("Nobody is left to draw in \"{}\"", giveaway.title)))?;
+        let text = self.text(&giveaway.channel, "giveaway.rerolled", &[
+            ("previous", &previous_name),
+            ("title", &giveaway.title),
+            ("winner", &winner.username),
+        ]);
+        self.announce(&giveaway, "rerolled", &text, json!({ "winners": [&winner], "replaced": previous_name })).await;
+        Ok(winner)
+    }
+
+    /// Weighted draw among entries still in the running; marks and stores the winner.
+    async fn pick(&self, entries: &mut [GiveawayEntry]) -> Result<Option<GiveawayEntry>, Error> {
+        let total = total_weight(entries);
+        if total == 0 {
+            return Ok(None);
+        }
+        let roll = rand::rng().random_range(0..total);
+        let Some(entry_id) = pick_weighted(entries, roll).map(|e| e.entry_id) else {
+            return Ok(None);
+        };
+        let entry = entries.iter_mut().find(|e| e.entry_id == entry_id).expect("picked from entries");
+        entry.is_winner = true;
+        entry.won_at = Some(Utc::now());
+        self.repo.update_entry(entry).await?;
+        Ok(Some(entry.clone()))
+    }
+
+    pub async fn get_giveaway(&self, giveaway_id: Uuid) -> Result<Giveaway, Error> {
+        self.repo.get_giveaway(giveaway_id).await?
+            .ok_or_else(|| Error::NotFound(format!("Giveaway {} not found", giveaway_id)))
+    }
+
+    pub async fn list_giveaways(&self, limit: i64) -> Result<Vec<Giveaway>, Error> {
+        self.repo.list_giveaways(limit).await
+    }
+
+    pub async fn list_entries(&self, giveaway_id: Uuid) -> Result<Vec<GiveawayEntry>, Error> {
+        self.repo.list_entries(giveaway_id).await
+    }
+
+    async fn delete_reward(&self, giveaway: &Giveaway) {
+        let Some(reward_id) = &giveaway.reward_id else { return };
+        let result = match broadcaster_helix(&*self.plugin_manager.credentials_repo).await {
+            Ok(helix) => helix.client.delete_custom_reward(&helix.broadcaster_id, reward_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!("[Giveaways] could not delete reward {} of '{}': {:?}", reward_id, giveaway.title, e);
+        }
+    }
+
+    async fn set_redemption_status(&self, reward_id: &str, redemption_id: &str, status: &str) -> Result<(), Error> {
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await?;
+        helix.client
+            .update_redemption_status(&helix.broadcaster_id, reward_id, &[redemption_id], status)
+            .await?;
+        Ok(())
+    }
+
+    /// Chat text in the channel's language.
+    fn text(&self, channel: &str, key: &str, args: &[(&str, &str)]) -> String {
+        i18n::text_for(self.plugin_manager.localizer.as_deref(), "twitch-irc", channel, key, args)
+    }
+
+    async fn say(&self, channel: &str, text: &str) {
+        let sender = MessageSender::new(
+            self.plugin_manager.credentials_repo.clone(),
+            self.plugin_manager.platform_manager.clone(),
+        );
+        if let Err(e) = sender.send_twitch_message(channel, text, None, Uuid::nil()).await {
+            warn!("[Giveaways] could not send to #{}: {:?}", channel, e);
+        }
+    }
+
+    /// Posts to chat and the configured Discord channel, and tells plugins.
+    async fn announce(&self, giveaway: &Giveaway, event: &str, text: &str, extra: serde_json::Value) {
+        if self.settings.get_bool("giveaways.announce_chat").unwrap_or(true) {
+            self.say(&giveaway.channel, text).await;
+        }
+
+        let setting = |key: &str| self.settings.get(key).filter(|v| !v.trim().is_empty());
+        if let (Some(account), Some(guild_id), Some(channel_id)) = (
+            setting("giveaways.discord_account"),
+            setting("giveaways.discord_guild_id"),
+            setting("giveaways.discord_channel_id"),
+        ) {
+            if let Err(e) = self.plugin_manager.platform_manager
+                .send_discord_message(&account, &guild_id, &channel_id, text)
+                .await
+            {
+                warn!("[Giveaways] could not announce on Discord: {:?}", e);
+            }
+        }
+
+        let mut payload = json!({ "event": event, "giveaway": giveaway, "text": text });
+        if let (Some(payload), Some(extra)) = (payload.as_object_mut(), extra.as_object()) {
+            payload.extend(extra.clone());
+        }
+        self.plugin_manager.broadcast(
+            PluginStreamResponse {
+                payload: Some(RespPayload::GameEvent(GameEvent {
+                    name: "giveaway".to_string(),
+                    json: payload.to_string(),
+                })),
+            },
+            None,
+        ).await;
+    }
+}
@@ -0,0 +1,438 @@
+// File: maowbot-core/src/services/twitch/hype_detector.rs
+//
+// Spots hype moments in the broadcaster's Twitch channel. Chat lines, emote
+// spam, bits and subs are counted over a sliding `hype.window_seconds`; when
+// one of them crosses its threshold (chat is compared with its own usual
+// rate) while the stream is live, the moment gets a stream marker, OBS saves
+// the replay buffer if asked to, and `BotEvent::HypeMoment` goes out for
+// recaps and the event pipeline. After a moment the detector rests for
+// `hype.cooldown_seconds`, so one long hype is one moment.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::models::stream_marker::MarkerSource;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::eventbus::{BotEvent, EventBus, HypeKind, TwitchEventSubData};
+use crate::platforms::manager::PlatformManager;
+use crate::services::twitch::broadcaster_helix;
+use crate::services::twitch::stream_marker_service::StreamMarkerService;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(5);
+/// Moments kept for `recent_moments`.
+const RECENT_MOMENTS: usize = 50;
+/// Weight of the newest window in the usual chat rate.
+const BASELINE_WEIGHT: f64 = 0.1;
+
+/// When each signal counts as hype; a threshold of 0 turns its signal off.
+#[derive(Debug, Clone, Copy)]
+pub struct HypeThresholds {
+    pub window: Duration,
+    /// Chat lines in a window, at the least, for a surge
+    pub min_messages: u32,
+    /// Chat rate, as a percentage of the usual rate, for a surge
+    pub surge_percent: u32,
+    pub emote_messages: u32,
+    pub bits: u64,
+    pub subs: u64,
+}
+
+/// A hype moment as it was detected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HypeReading {
+    pub kind: HypeKind,
+    pub score: f64,
+    pub messages: u32,
+    pub emote_messages: u32,
+    pub bits: u64,
+    pub subs: u64,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Counts over the sliding window, plus the usual chat rate.
+#[derive(Debug, Default)]
+pub struct HypeWindow {
+    /// (when, message id, emote-heavy)
+    messages: VecDeque<(DateTime<Utc>, String, bool)>,
+    bits: VecDeque<(DateTime<Utc>, u64)>,
+    subs: VecDeque<(DateTime<Utc>, u64)>,
+    /// Chat lines per window, smoothed; None until a window has passed
+    baseline: Option<f64>,
+    baseline_at: Option<DateTime<Utc>>,
+}
+
+impl HypeWindow {
+    /// Counts a chat line; the same message id twice (several bot accounts in
+    /// the channel) counts once.
+    pub fn add_message(&mut self, at: DateTime<Utc>, message_id: &str, emote_heavy: bool) {
+        if !message_id.is_empty() && self.messages.iter().any(|(_, id, _)| id == message_id) {
+            return;
+        }
+        self.messages.push_back((at, message_id.to_string(), emote_heavy));
+    }
+
+    pub fn add_bits(&mut self, at: DateTime<Utc>, bits: u64) {
+        self.bits.push_back((at, bits));
+    }
+
+    pub fn add_subs(&mut self, at: DateTime<Utc>, subs: u64) {
+        self.subs.push_back((at, subs));
+    }
+
+    /// Drops what fell out of the window and checks the thresholds. The
+    /// usual chat rate is updated once per window, after the check, so a
+    /// surge is measured against what came before it.
+    pub fn evaluate(&mut self, now: DateTime<Utc>, t: &HypeThresholds) -> Option<HypeReading> {
+        let since = now - t.window;
+        while self.messages.front().is_some_and(|(at, _, _)| *at < since) {
+            self.messages.pop_front();
+        }
+        while self.bits.front().is_some_and(|(at, _)| *at < since) {
+            self.bits.pop_front();
+        }
+        while self.subs.front().is_some_and(|(at, _)| *at < since) {
+            self.subs.pop_front();
+        }
+
+        let messages = self.messages.len() as u32;
+        let emote_messages = self.messages.iter().filter(|(_, _, heavy)| *heavy).count() as u32;
+        let bits: u64 = self.bits.iter().map(|(_, b)| b).sum();
+        let subs: u64 = self.subs.iter().map(|(_, s)| s).sum();
+
+        let mut ratios = Vec::with_capacity(4);
+        if t.min_messages > 0 {
+            // Without a usual rate yet, only the minimum applies
+            let usual = self.baseline.unwrap_or(0.0) * t.surge_percent as f64 / 100.0;
+            ratios.push((HypeKind::ChatSurge, messages as f64 / usual.max(t.min_messages as f64)));
+        }
+        if t.emote_messages > 0 {
+            ratios.push((HypeKind::EmoteBurst, emote_messages as f64 / t.emote_messages as f64));
+        }
+        if t.bits > 0 {
+            ratios.push((HypeKind::Bits, bits as f64 / t.bits as f64));
+        }
+        if t.subs > 0 {
+            ratios.push((HypeKind::Subs, subs as f64 / t.subs as f64));
+        }
+
+        if self.baseline_at.is_none_or(|at| now - at >= t.window) {
+            self.baseline = Some(match self.baseline {
+                Some(usual) => usual * (1.0 - BASELINE_WEIGHT) + messages as f64 * BASELINE_WEIGHT,
+                None => messages as f64,
+            });
+            self.baseline_at = Some(now);
+        }
+
+        let (kind, top) = ratios.iter().copied().max_by(|a, b| a.1.total_cmp(&b.1))?;
+        if top < 1.0 {
+            return None;
+        }
+        Some(HypeReading {
+            kind,
+            score: ratios.iter().map(|(_, r)| r).filter(|r| **r >= 1.0).sum(),
+            messages,
+            emote_messages,
+            bits,
+            subs,
+            started_at: since,
+        })
+    }
+
+    /// Forgets the window so the next moment needs fresh activity.
+    pub fn reset_counts(&mut self) {
+        self.messages.clear();
+        self.bits.clear();
+        self.subs.clear();
+    }
+}
+
+/// Whether a chat line is mostly emotes, from its Twitch `emotes` tag
+/// ("25:0-4,12-16/1902:6-10").
+pub fn is_emote_heavy(text: &str, emotes_tag: Option<&str>) -> bool {
+    let emotes: usize = emotes_tag.unwrap_or_default()
+        .split('/')
+        .filter_map(|group| group.split_once(':'))
+        .map(|(_, ranges)| ranges.split(',').count())
+        .sum();
+    emotes > 0 && text.split_whitespace().count() <= emotes * 2
+}
+
+#[derive(Default)]
+struct DetectorState {
+    /// The broadcaster's login; None until the first check finds one
+    channel: Option<String>,
+    window: HypeWindow,
+    last_moment: Option<DateTime<Utc>>,
+    recent: VecDeque<HypeReading>,
+}
+
+pub struct HypeDetector {
+    stream_markers: Arc<StreamMarkerService>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    state: Mutex<DetectorState>,
+}
+
+impl HypeDetector {
+    pub fn new(
+        stream_markers: Arc<StreamMarkerService>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            stream_markers,
+            credentials_repo,
+            platform_manager,
+            event_bus,
+            settings,
+            state: Mutex::new(DetectorState::default()),
+        }
+    }
+
+    fn thresholds(&self) -> HypeThresholds {
+        let get = |key: &str, default: u64| self.settings.get_u64(key).unwrap_or(default);
+        HypeThresholds {
+            window: Duration::seconds(get("hype.window_seconds", 30).max(10) as i64),
+            min_messages: get("hype.min_messages", 25) as u32,
+            surge_percent: get("hype.chat_surge_percent", 300) as u32,
+            emote_messages: get("hype.emote_messages", 15) as u32,
+            bits: get("hype.bits", 500),
+            subs: get("hype.subs", 5),
+        }
+    }
+
+    /// Counts chat and Twitch events, and checks for hype every few seconds
+    /// while `hype.enabled`.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event),
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Hype] event loop stopped");
+        });
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                if service.settings.get_bool("hype.enabled").unwrap_or(true) {
+                    if let Err(e) = service.check().await {
+                        debug!("[Hype] check failed: {:?}", e);
+                    }
+                } else {
+                    *service.state.lock() = DetectorState::default();
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Hype] check loop stopped");
+        });
+    }
+
+    fn handle_event(&self, event: BotEvent) {
+        let mut state = self.state.lock();
+        let Some(own_channel) = state.channel.clone() else { return };
+        match event {
+            BotEvent::ChatMessage { platform, channel, text, timestamp, metadata, .. } => {
+                if platform != "twitch-irc" || channel.trim_start_matches('#').to_lowercase() != own_channel {
+                    return;
+                }
+                let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+                let heavy = is_emote_heavy(&text, Some(field("emotes")));
+                state.window.add_message(timestamp, field("message_id"), heavy);
+            }
+            BotEvent::TwitchEventSub(data) => {
+                let now = Utc::now();
+                match data {
+                    TwitchEventSubData::ChannelCheer(evt) if evt.broadcaster_user_login == own_channel => {
+                        state.window.add_bits(now, evt.bits);
+                    }
+                    // Gifted subs also arrive one by one as channel.subscribe; count the gift once
+                    TwitchEventSubData::ChannelSubscribe(evt) if !evt.is_gift && evt.broadcaster_user_login == own_channel => {
+                        state.window.add_subs(now, 1);
+                    }
+                    TwitchEventSubData::ChannelSubscriptionMessage(evt) if evt.broadcaster_user_login == own_channel => {
+                        state.window.add_subs(now, 1);
+                    }
+                    TwitchEventSubData::ChannelSubscriptionGift(evt) if evt.broadcaster_user_login == own_channel => {
+                        state.window.add_subs(now, evt.total);
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn check(&self) -> Result<(), Error> {
+        let Some(cred) = self.credentials_repo.get_broadcaster_credential(&Platform::Twitch).await? else {
+            *self.state.lock() = DetectorState::default();
+            return Ok(());
+        };
+        let channel = cred.user_name.to_lowercase();
+        let now = Utc::now();
+        let thresholds = self.thresholds();
+        let cooldown = Duration::seconds(self.settings.get_u64("hype.cooldown_seconds").unwrap_or(180) as i64);
+
+        let reading = {
+            let mut state = self.state.lock();
+            if state.channel.as_deref() != Some(channel.as_str()) {
+                *state = DetectorState { channel: Some(channel.clone()), ..DetectorState::default() };
+                return Ok(());
+            }
+            let reading = state.window.evaluate(now, &thresholds);
+            if state.last_moment.is_some_and(|at| now - at < cooldown) {
+                return Ok(());
+            }
+            let Some(reading) = reading else { return Ok(()) };
+            state.last_moment = Some(now);
+            state.window.reset_counts();
+            reading
+        };
+
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        if broadcaster.client.fetch_live_stream(&broadcaster.broadcaster_id).await?.is_none() {
+            debug!("[Hype] {} moment while offline; ignored", reading.kind.as_str());
+            return Ok(());
+        }
+        self.fire(&channel, reading, now).await;
+        Ok(())
+    }
+
+    async fn fire(&self, channel: &str, reading: HypeReading, now: DateTime<Utc>) {
+        let summary = match reading.kind {
+            HypeKind::ChatSurge => format!("chat surge ({} messages)", reading.messages),
+            HypeKind::EmoteBurst => format!("emote burst ({} emote messages)", reading.emote_messages),
+            HypeKind::Bits => format!("{} bits", reading.bits),
+            HypeKind::Subs => format!("{} subs", reading.subs),
+        };
+        info!("[Hype] {} in {} (score {:.2})", summary, channel, reading.score);
+
+        if self.settings.get_bool("hype.create_marker").unwrap_or(true) {
+            if let Err(e) = self.stream_markers.create_marker(MarkerSource::Hype, &format!("Hype: {}", summary)).await {
+                debug!("[Hype] no marker: {:?}", e);
+            }
+        }
+        if self.settings.get_bool("hype.save_replay").unwrap_or(false) {
+            let instance = self.settings.get_u64("hype.obs_instance").unwrap_or(1) as u32;
+            match self.platform_manager.get_obs_instance(instance).await {
+                Ok(obs) => {
+                    if let Err(e) = obs.get_client().save_replay_buffer().await {
+                        warn!("[Hype] OBS {} could not save the replay buffer: {}", instance, e);
+                    }
+                }
+                Err(e) => warn!("[Hype] OBS {} unavailable: {:?}", instance, e),
+            }
+        }
+
+        {
+            let mut state = self.state.lock();
+            state.recent.push_back(reading.clone());
+            while state.recent.len() > RECENT_MOMENTS {
+                state.recent.pop_front();
+            }
+        }
+        self.event_bus.publish(BotEvent::HypeMoment {
+            channel: channel.to_string(),
+            kind: reading.kind,
+            score: reading.score,
+            messages: reading.messages,
+            emote_messages: reading.emote_messages,
+            bits: reading.bits,
+            subs: reading.subs,
+            started_at: reading.started_at,
+            timestamp: now,
+        }).await;
+    }
+
+    /// The latest moments since the server started, oldest first.
+    pub fn recent_moments(&self) -> Vec<HypeReading> {
+        self.state.lock().recent.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> HypeThresholds {
+        HypeThresholds {
+            window: Duration::seconds(30),
+            min_messages: 10,
+            surge_percent: 300,
+            emote_messages: 8,
+            bits: 500,
+            subs: 5,
+        }
+    }
+
+    #[test]
+    fn test_chat_surges_are_measured_against_the_usual_rate() {
+        let t = thresholds();
+        let start = Utc::now();
+        let mut window = HypeWindow::default();
+
+        // A steady chat for a while: 4 lines every 30 seconds
+        for round in 0..10 {
+            let at = start + Duration::seconds(30 * round);
+            for i in 0..4 {
+                window.add_message(at, &format!("{}-{}", round, i), false);
+            }
+            assert_eq!(window.evaluate(at, &t), None, "steady chat is not hype (round {})", round);
+        }
+
+        let at = start + Duration::seconds(330);
+        for i in 0..40 {
+            window.add_message(at, &format!("surge-{}", i), false);
+            window.add_message(at, &format!("surge-{}", i), false);
+        }
+        let reading = window.evaluate(at, &t).expect("surge");
+        assert_eq!(reading.kind, HypeKind::ChatSurge);
+        assert_eq!(reading.messages, 40);
+    }
+
+    #[test]
+    fn test_bits_and_emotes_trip_their_own_thresholds() {
+        let t = thresholds();
+        let now = Utc::now();
+        let mut window = HypeWindow::default();
+        window.add_bits(now, 1000);
+        window.add_subs(now, 2);
+        assert_eq!(window.evaluate(now, &t).map(|r| r.kind), Some(HypeKind::Bits));
+
+        assert!(is_emote_heavy("PogChamp PogChamp", Some("305954156:0-7,9-16")));
+        assert!(!is_emote_heavy("what a play PogChamp that was insane", Some("305954156:12-19")));
+        assert!(!is_emote_heavy("no emotes here", None));
+    }
+}
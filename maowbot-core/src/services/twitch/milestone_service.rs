@@ -0,0 +1,344 @@
+// File: maowbot-core/src/services/twitch/milestone_service.rs
+//
+// Celebrates viewer milestones in the broadcaster's Twitch channel: sub
+// anniversaries (every 12 months subscribed), sub streaks listed in
+// `milestones.streak_months`, and follow anniversaries. Subs are taken from
+// the resub messages viewers share; the last one is stored, so an anniversary
+// crossed without sharing the resub in between still counts. Follow dates are
+// stored from follow events, or looked up on Helix the first time a viewer
+// chats, and a follow anniversary is celebrated on the viewer's next chat
+// message within `milestones.follow_grace_days`. Each milestone is celebrated
+// once per viewer: a chat message, a `milestone` GameEvent for overlays, a
+// pulse of the OSC parameter in `milestones.osc_parameter`, and
+// `BotEvent::ViewerMilestone` for pipelines.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::milestone::{MilestoneKind, SubHistory, ViewerMilestone};
+use maowbot_common::traits::repository_traits::MilestoneRepository;
+use maowbot_proto::plugs::{
+    plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse,
+};
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData};
+use crate::platforms::twitch_eventsub::events::{ChannelFollow, ChannelSubscriptionMessage};
+use crate::plugins::manager::PluginManager;
+use crate::services::message_sender::MessageSender;
+use crate::services::twitch::broadcaster_channel::BroadcasterChannel;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use crate::i18n;
+
+const DEFAULT_STREAK_MONTHS: &str = "3, 6, 12, 24, 36";
+
+/// Parses a list of months such as "3, 6, 12"; entries that aren't positive
+/// numbers are skipped.
+pub fn parse_months(text: &str) -> Vec<u32> {
+    text.split(',')
+        .filter_map(|m| m.trim().parse::<u32>().ok())
+        .filter(|m| *m > 0)
+        .collect()
+}
+
+/// The milestone a resub reached, if any. An anniversary wins over a streak
+/// in the same message. `previous_months` is the last resub that was shared;
+/// every anniversary since then counts, the latest one is celebrated.
+pub fn sub_milestone(
+    previous_months: Option<u32>,
+    cumulative_months: u32,
+    streak_months: Option<u32>,
+    streak_marks: &[u32],
+) -> Option<(MilestoneKind, i32)> {
+    let previous = previous_months
+        .filter(|p| *p < cumulative_months)
+        .unwrap_or(cumulative_months.saturating_sub(1));
+    let years = cumulative_months / 12;
+    if years >= 1 && years > previous / 12 {
+        return Some((MilestoneKind::SubAnniversary, years as i32));
+    }
+    streak_months
+        .filter(|s| streak_marks.contains(s))
+        .map(|s| (MilestoneKind::SubStreak, s as i32))
+}
+
+/// Full years since `followed_at` when the latest anniversary was at most
+/// `grace_days` ago, counting by calendar date.
+pub fn follow_anniversary(followed_at: DateTime<Utc>, now: DateTime<Utc>, grace_days: i64) -> Option<i32> {
+    let (followed, today) = (followed_at.date_naive(), now.date_naive());
+    let mut years = today.year() - followed.year();
+    if (today.month(), today.day()) < (followed.month(), followed.day()) {
+        years -= 1;
+    }
+    if years < 1 {
+        return None;
+    }
+    // Follows on Feb 29 have their anniversary on Feb 28 in other years
+    let anniversary = followed.with_year(followed.year() + years)
+        .or_else(|| NaiveDate::from_ymd_opt(followed.year() + years, 2, 28))?;
+    let since = (today - anniversary).num_days();
+    (0..=grace_days.max(0)).contains(&since).then_some(years)
+}
+
+#[derive(Default)]
+struct MilestoneState {
+    /// Twitch user ids whose follow anniversary was checked on `checked_on`
+    follow_checked: HashSet<String>,
+    checked_on: Option<NaiveDate>,
+}
+
+pub struct MilestoneService {
+    repo: Arc<dyn MilestoneRepository>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    channel: BroadcasterChannel,
+    state: Mutex<MilestoneState>,
+}
+
+impl MilestoneService {
+    pub fn new(
+        repo: Arc<dyn MilestoneRepository>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            repo,
+            event_bus,
+            settings,
+            plugin_manager,
+            channel: BroadcasterChannel::new(),
+            state: Mutex::new(MilestoneState::default()),
+        }
+    }
+
+    /// Watches follows, resub messages and the broadcaster's chat.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Milestones] event loop stopped");
+        });
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        if !self.settings.get_bool("milestones.enabled").unwrap_or(true) {
+            return;
+        }
+        let result = match event {
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelFollow(follow)) => self.on_follow(&follow).await,
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelSubscriptionMessage(resub)) => self.on_resub(&resub).await,
+            BotEvent::ChatMessage { platform, channel, metadata, .. } if platform == "twitch-irc" => {
+                let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                self.on_chat(&channel, &field("platform_user_id"), &field("username")).await
+            }
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("[Milestones] {:?}", e);
+        }
+    }
+
+    async fn on_follow(&self, follow: &ChannelFollow) -> Result<(), Error> {
+        let channel = follow.broadcaster_user_login.to_lowercase();
+        self.repo.upsert_follow_date(&channel, &follow.user_id, follow.followed_at).await
+    }
+
+    async fn on_resub(&self, resub: &ChannelSubscriptionMessage) -> Result<(), Error> {
+        let channel = resub.broadcaster_user_login.to_lowercase();
+        let previous = self.repo.get_sub_history(&channel, &resub.user_id).await?;
+        self.repo.upsert_sub_history(&SubHistory {
+            channel: channel.clone(),
+            twitch_user_id: resub.user_id.clone(),
+            cumulative_months: resub.cumulative_months as i32,
+            streak_months: resub.streak_months.map(|s| s as i32),
+            updated_at: Utc::now(),
+        }).await?;
+
+        let marks = parse_months(&self.settings.get("milestones.streak_months")
+            .unwrap_or_else(|| DEFAULT_STREAK_MONTHS.to_string()));
+        let previous_months = previous.map(|h| h.cumulative_months.max(0) as u32);
+        let Some((kind, value)) = sub_milestone(previous_months, resub.cumulative_months, resub.streak_months, &marks) else {
+            return Ok(());
+        };
+        self.celebrate(ViewerMilestone {
+            milestone_id: Uuid::new_v4(),
+            channel,
+            twitch_user_id: resub.user_id.clone(),
+            user_login: resub.user_login.clone(),
+            display_name: resub.user_name.clone(),
+            kind,
+            value,
+            celebrated_at: Utc::now(),
+        }).await;
+        Ok(())
+    }
+
+    /// Checks a chatter's follow anniversary, once a day per chatter.
+    async fn on_chat(&self, channel: &str, twitch_user_id: &str, display_name: &str) -> Result<(), Error> {
+        if twitch_user_id.is_empty() || !self.settings.get_bool("milestones.follow_anniversaries").unwrap_or(true) {
+            return Ok(());
+        }
+        let channel = channel.trim_start_matches('#').to_lowercase();
+        if self.broadcaster_channel().await.as_deref() != Some(channel.as_str()) {
+            return Ok(());
+        }
+        {
+            let mut state = self.state.lock();
+            let today = Utc::now().date_naive();
+            if state.checked_on != Some(today) {
+                state.checked_on = Some(today);
+                state.follow_checked.clear();
+            }
+            if !state.follow_checked.insert(twitch_user_id.to_string()) {
+                return Ok(());
+            }
+        }
+
+        let followed_at = match self.repo.get_follow_date(&channel, twitch_user_id).await? {
+            Some(at) => at,
+            None => {
+                let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await?;
+                let Some(at) = helix.client.fetch_follow_date(twitch_user_id, &helix.broadcaster_id).await? else {
+                    return Ok(());
+                };
+                self.repo.upsert_follow_date(&channel, twitch_user_id, at).await?;
+                at
+            }
+        };
+        let grace_days = self.settings.get_i64("milestones.follow_grace_days").unwrap_or(7);
+        let Some(years) = follow_anniversary(followed_at, Utc::now(), grace_days) else {
+            return Ok(());
+        };
+        self.celebrate(ViewerMilestone {
+            milestone_id: Uuid::new_v4(),
+            channel,
+            twitch_user_id: twitch_user_id.to_string(),
+            user_login: display_name.to_lowercase(),
+            display_name: display_name.to_string(),
+            kind: MilestoneKind::FollowAnniversary,
+            value: years,
+            celebrated_at: Utc::now(),
+        }).await;
+        Ok(())
+    }
+
+    /// The broadcaster's login, looked up once and again after failures.
+    async fn broadcaster_channel(&self) -> Option<String> {
+        self.channel.resolve(&*self.plugin_manager.credentials_repo).await
+    }
+
+    /// Stores the milestone and, the first time, celebrates it the ways the
+    /// settings ask for.
+    async fn celebrate(&self, milestone: ViewerMilestone) {
+        match self.repo.record_milestone(&milestone).await {
+            Ok(true) => {}
+            Ok(false) => {
+                debug!("[Milestones] {} already had {} {}", milestone.display_name, milestone.kind, milestone.value);
+                return;
+            }
+            Err(e) => {
+                warn!("[Milestones] could not store {} of {}: {:?}", milestone.kind, milestone.display_name, e);
+                return;
+            }
+        }
+        info!("[Milestones] {} reached {} {} {}", milestone.display_name, milestone.kind, milestone.value, milestone.kind.unit());
+
+        if self.settings.get_bool("milestones.chat").unwrap_or(true) {
+            let key = format!("milestone.{}", milestone.kind);
+            let text = i18n::text_for(self.plugin_manager.localizer.as_deref(), "twitch-irc", &milestone.channel, &key, &[
+                ("user", &milestone.display_name),
+                ("value", &milestone.value.to_string()),
+            ]);
+            let sender = MessageSender::new(
+                self.plugin_manager.credentials_repo.clone(),
+                self.plugin_manager.platform_manager.clone(),
+            );
+            if let Err(e) = sender.send_twitch_message(&milestone.channel, &text, None, Uuid::nil()).await {
+                warn!("[Milestones] could not send to #{}: {:?}", milestone.channel, e);
+            }
+        }
+
+        if self.settings.get_bool("milestones.overlay").unwrap_or(true) {
+            let payload = serde_json::json!({
+                "milestone": milestone,
+                "unit": milestone.kind.unit(),
+            });
+            self.plugin_manager.broadcast(
+                PluginStreamResponse {
+                    payload: Some(RespPayload::GameEvent(GameEvent {
+                        name: "milestone".to_string(),
+                        json: payload.to_string(),
+                    })),
+                },
+                None,
+            ).await;
+        }
+
+        let parameter = self.settings.get("milestones.osc_parameter").unwrap_or_default();
+        if !parameter.trim().is_empty() {
+            let seconds = self.settings.get_u64("milestones.osc_seconds").unwrap_or(5);
+            match &self.plugin_manager.osc_toggle_service {
+                Some(osc) => {
+                    if let Err(e) = osc.pulse_parameter(parameter.trim(), seconds).await {
+                        warn!("[Milestones] could not pulse {}: {}", parameter.trim(), e);
+                    }
+                }
+                None => debug!("[Milestones] no OSC toggle service to pulse {}", parameter.trim()),
+            }
+        }
+
+        self.event_bus.publish(BotEvent::ViewerMilestone(milestone)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_anniversaries_win_and_crossed_ones_count() {
+        let marks = parse_months(DEFAULT_STREAK_MONTHS);
+        assert_eq!(sub_milestone(None, 12, Some(12), &marks), Some((MilestoneKind::SubAnniversary, 1)));
+        assert_eq!(sub_milestone(None, 13, Some(6), &marks), Some((MilestoneKind::SubStreak, 6)));
+        assert_eq!(sub_milestone(None, 13, None, &marks), None);
+        // 23 -> 25 without sharing month 24
+        assert_eq!(sub_milestone(Some(23), 25, None, &marks), Some((MilestoneKind::SubAnniversary, 2)));
+        // A stale or repeated history entry only looks back one month
+        assert_eq!(sub_milestone(Some(30), 25, Some(5), &marks), None);
+    }
+
+    #[test]
+    fn test_follow_anniversaries_have_a_grace_period() {
+        let followed = Utc.with_ymd_and_hms(2023, 3, 10, 18, 0, 0).unwrap();
+        let on = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap();
+        assert_eq!(follow_anniversary(followed, on(2024, 3, 10), 7), Some(1));
+        assert_eq!(follow_anniversary(followed, on(2025, 3, 17), 7), Some(2));
+        assert_eq!(follow_anniversary(followed, on(2025, 3, 18), 7), None);
+        assert_eq!(follow_anniversary(followed, on(2024, 3, 9), 7), None);
+
+        let leap = Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap();
+        assert_eq!(follow_anniversary(leap, on(2021, 3, 1), 1), Some(1));
+    }
+}
@@ -5,7 +5,75 @@
 pub mod command_service;
 pub mod redeem_service;
 pub mod eventsub_service;
+pub mod stream_marker_service;
+pub mod giveaway_service;
+pub mod crowd_vote_service;
+pub mod protection_service;
+pub mod bot_detection;
+pub mod clip_service;
+pub mod redeem_schedule_service;
+pub mod redeem_approval_service;
+pub mod scope_check;
+pub mod chatter_presence;
+pub mod schedule_service;
+pub mod hype_detector;
+pub mod milestone_service;
+pub mod stream_session_service;
+pub mod broadcaster_channel;
 
 pub mod builtin_commands;
 pub mod builtin_redeems;
-pub mod event_actions;
\ No newline at end of file
+pub mod event_actions;
+
+use maowbot_common::models::platform::Platform;
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::platforms::manager::PlatformManager;
+use crate::platforms::twitch::routing::TwitchOperation;
+use crate::Error;
+
+/// A Helix client on the broadcaster's token, with who the broadcaster is.
+pub struct BroadcasterHelix {
+    pub broadcaster_id: String,
+    pub login: String,
+    pub client: TwitchHelixClient,
+}
+
+/// Looks up the broadcaster's Twitch credential, for Helix calls that need
+/// the channel owner's scopes.
+pub async fn broadcaster_helix(
+    credentials_repo: &(dyn CredentialsRepository + Send + Sync),
+) -> Result<BroadcasterHelix, Error> {
+    let cred = credentials_repo
+        .get_broadcaster_credential(&Platform::Twitch)
+        .await?
+        .ok_or_else(|| Error::Platform(
+            "No Twitch login is marked as the broadcaster; add it with `account add twitch <name>` \
+             or mark it with `account type twitch <name> broadcaster`".into()
+        ))?;
+    let broadcaster_id = cred.platform_id.clone()
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| Error::Platform("Broadcaster credential missing platform_id".into()))?;
+    let client_id = cred.additional_data.as_ref()
+        .and_then(|d| d.get("client_id").or_else(|| d.get("validate_client_id")))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::Platform("Broadcaster credential missing client_id".into()))?;
+    Ok(BroadcasterHelix {
+        broadcaster_id,
+        login: cred.user_name.to_lowercase(),
+        client: TwitchHelixClient::new(&cred.primary_token, client_id),
+    })
+}
+
+/// Whispers `text` to a Twitch user from the account routed for whispers
+/// (the bot if one is set up, otherwise the broadcaster).
+pub async fn send_whisper(
+    platform_manager: &PlatformManager,
+    to_user_id: &str,
+    text: &str,
+) -> Result<(), Error> {
+    let helix = platform_manager.twitch_accounts()?.helix_for(TwitchOperation::Whispers).await?;
+    helix.client
+        .send_whisper(&helix.acting_id, to_user_id, text)
+        .await
+}
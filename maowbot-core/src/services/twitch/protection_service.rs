@@ -0,0 +1,626 @@
+// File: maowbot-core/src/services/twitch/protection_service.rs
+//
+// Raid defense for the broadcaster's Twitch chat. Chat in the broadcaster's
+// channel is watched over a short sliding window; when the spike heuristic
+// fires (message rate, identical messages from many chatters, or a high share
+// of brand-new accounts) chat is locked down with follower-only and slow mode,
+// and optionally Shield Mode. Each lockdown is logged as an incident together
+// with the messages captured while it lasted, and lifted again once chat has
+// been calm for a while.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde_json::json;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::protection::{
+    detect_spike, CapturedMessage, IncidentTrigger, ProtectionIncident, SpikeThresholds,
+};
+use maowbot_common::traits::repository_traits::ProtectionIncidentRepository;
+use maowbot_proto::plugs::{
+    plugin_stream_response::Payload as RespPayload, GameEvent, PluginStreamResponse,
+};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::twitch::requests::moderation::ChatSettings;
+use crate::plugins::manager::PluginManager;
+use crate::services::message_dedupe::MessageDedupe;
+use crate::services::message_sender::MessageSender;
+use crate::services::twitch::broadcaster_channel::BroadcasterChannel;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+use crate::i18n;
+
+/// Chat messages kept in the sliding window, whatever its length.
+const MAX_WINDOW_MESSAGES: usize = 2000;
+/// Messages stored with a single incident.
+const MAX_CAPTURED_MESSAGES: usize = 1000;
+/// Account ages looked up per tick.
+const MAX_AGE_LOOKUPS: usize = 100;
+/// The account age cache is dropped once it grows past this.
+const MAX_CACHED_ACCOUNTS: usize = 20_000;
+/// How often captured messages of a running incident are saved.
+const FLUSH_INTERVAL_SECS: i64 = 30;
+
+/// What the service is doing right now.
+#[derive(Debug, Clone)]
+pub struct ProtectionStatus {
+    pub auto_enabled: bool,
+    /// None when Twitch couldn't be asked
+    pub shield_mode: Option<bool>,
+    pub channel: Option<String>,
+    /// The running lockdown, without its captured messages
+    pub active_incident: Option<ProtectionIncident>,
+    pub window_messages: usize,
+}
+
+struct Lockdown {
+    incident: ProtectionIncident,
+    /// Chat settings to restore, if we changed them
+    previous: Option<ChatSettings>,
+    enabled_shield: bool,
+    last_spike: DateTime<Utc>,
+    last_flush: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct ProtectionState {
+    window: VecDeque<CapturedMessage>,
+    account_created: HashMap<String, DateTime<Utc>>,
+    /// Ids Helix returned nothing for
+    unknown_accounts: HashSet<String>,
+    /// Scores from bot detection, by Twitch user id
+    bot_scores: HashMap<String, f32>,
+    lockdown: Option<Lockdown>,
+    /// Every joined IRC account gets its own copy of a chat line; count it once
+    seen: MessageDedupe,
+}
+
+impl ProtectionState {
+    /// Adds a chat line to the window, and to the running incident's capture.
+    fn observe(&mut self, message_id: &str, message: CapturedMessage) {
+        if !message_id.is_empty() && !self.seen.first_sighting(message_id) {
+            return;
+        }
+        if let Some(lockdown) = self.lockdown.as_mut() {
+            if lockdown.incident.messages.len() < MAX_CAPTURED_MESSAGES {
+                lockdown.incident.messages.push(message.clone());
+            }
+        }
+        self.window.push_back(message);
+        while self.window.len() > MAX_WINDOW_MESSAGES {
+            self.window.pop_front();
+        }
+    }
+}
+
+pub struct ProtectionService {
+    repo: Arc<dyn ProtectionIncidentRepository>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    plugin_manager: Arc<PluginManager>,
+    /// The channel we protect
+    channel: BroadcasterChannel,
+    state: Mutex<ProtectionState>,
+    /// Keeps a detected spike and a moderator command from locking down twice
+    transition: tokio::sync::Mutex<()>,
+}
+
+impl ProtectionService {
+    pub fn new(
+        repo: Arc<dyn ProtectionIncidentRepository>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+        plugin_manager: Arc<PluginManager>,
+    ) -> Self {
+        Self {
+            repo,
+            event_bus,
+            settings,
+            plugin_manager,
+            channel: BroadcasterChannel::new(),
+            state: Mutex::new(ProtectionState::default()),
+            transition: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Closes incidents left open by a restart, then watches chat and checks
+    /// the window once a second.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            service.close_stale_incidents().await;
+
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event),
+                        None => break,
+                    },
+                    _ = interval.tick() => service.tick().await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            service.flush().await;
+            debug!("[Protection] event loop stopped");
+        });
+    }
+
+    async fn close_stale_incidents(&self) {
+        let open = match self.repo.list_incidents(20).await {
+            Ok(incidents) => incidents.into_iter().filter(|i| i.ended_at.is_none()),
+            Err(e) => {
+                warn!("[Protection] could not load incidents: {:?}", e);
+                return;
+            }
+        };
+        for incident in open {
+            // list_incidents leaves messages out, so reload before saving
+            let Ok(Some(mut incident)) = self.repo.get_incident(incident.incident_id).await else { continue };
+            incident.ended_at = Some(Utc::now());
+            incident.ended_by = Some("restart".to_string());
+            if let Err(e) = self.repo.update_incident(&incident).await {
+                warn!("[Protection] could not close incident {}: {:?}", incident.incident_id, e);
+            }
+        }
+    }
+
+    fn handle_event(&self, event: BotEvent) {
+        let BotEvent::ChatMessage { platform, channel, text, timestamp, metadata, .. } = event else { return };
+        if platform != "twitch-irc" {
+            return;
+        }
+        if !self.channel.is(&channel) {
+            return;
+        }
+        let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let message = CapturedMessage {
+            platform_user_id: field("platform_user_id"),
+            username: field("username"),
+            text,
+            sent_at: timestamp,
+        };
+        self.state.lock().observe(&field("message_id"), message);
+    }
+
+    fn thresholds(&self) -> SpikeThresholds {
+        let int = |key: &str| self.settings.get_i64(key).unwrap_or(0).max(0);
+        SpikeThresholds {
+            window_secs: int("protection.window_secs").max(1),
+            messages_per_sec: int("protection.messages_per_sec"),
+            identical_messages: int("protection.identical_messages") as usize,
+            new_account_days: int("protection.new_account_days"),
+            new_account_percent: int("protection.new_account_percent"),
+            min_chatters: int("protection.min_chatters") as usize,
+            likely_bot_percent: int("protection.likely_bot_percent"),
+            likely_bot_score: int("protection.likely_bot_score") as f32 / 100.0,
+        }
+    }
+
+    async fn tick(&self) {
+        let now = Utc::now();
+        self.refresh_channel().await;
+
+        let thresholds = self.thresholds();
+        let auto = self.settings.get_bool("protection.enabled").unwrap_or(false);
+        {
+            let mut state = self.state.lock();
+            let since = now - Duration::seconds(thresholds.window_secs);
+            while state.window.front().is_some_and(|m| m.sent_at < since) {
+                state.window.pop_front();
+            }
+        }
+
+        if auto {
+            if thresholds.new_account_percent > 0 {
+                self.lookup_account_ages().await;
+            }
+            let spike = {
+                let mut state = self.state.lock();
+                let state = &mut *state;
+                detect_spike(state.window.make_contiguous(), &state.account_created, &state.bot_scores, &thresholds, now)
+            };
+            if let Some(spike) = spike {
+                let already_locked = {
+                    let mut state = self.state.lock();
+                    match state.lockdown.as_mut() {
+                        Some(lockdown) => {
+                            lockdown.last_spike = now;
+                            true
+                        }
+                        None => false,
+                    }
+                };
+                if !already_locked {
+                    info!("[Protection] spike detected: {}", spike.details);
+                    if let Err(e) = self.lock_down(spike.trigger, spike.details).await {
+                        warn!("[Protection] could not lock down chat: {:?}", e);
+                    }
+                }
+            }
+        }
+
+        let calm_minutes = self.settings.get_i64("protection.calm_minutes").unwrap_or(0);
+        let (calm, flush_due) = {
+            let state = self.state.lock();
+            match &state.lockdown {
+                Some(l) => (
+                    calm_minutes > 0 && now - l.last_spike >= Duration::minutes(calm_minutes),
+                    now - l.last_flush >= Duration::seconds(FLUSH_INTERVAL_SECS),
+                ),
+                None => (false, false),
+            }
+        };
+        if calm {
+            if let Err(e) = self.lift("auto").await {
+                warn!("[Protection] could not lift lockdown: {:?}", e);
+            }
+        } else if flush_due {
+            self.flush().await;
+        }
+    }
+
+    /// Learns which channel is the broadcaster's, retrying now and then
+    /// while there's no broadcaster credential.
+    async fn refresh_channel(&self) {
+        let known = self.channel.get().is_some();
+        let login = self.channel.resolve(&*self.plugin_manager.credentials_repo).await;
+        if let Some(login) = login.filter(|_| !known) {
+            debug!("[Protection] watching #{}", login);
+        }
+    }
+
+    async fn lookup_account_ages(&self) {
+        let missing: Vec<String> = {
+            let state = self.state.lock();
+            let mut seen = HashSet::new();
+            state.window.iter()
+                .map(|m| &m.platform_user_id)
+                .filter(|id| !id.is_empty()
+                    && !state.account_created.contains_key(*id)
+                    && !state.unknown_accounts.contains(*id))
+                .filter(|id| seen.insert((*id).clone()))
+                .take(MAX_AGE_LOOKUPS)
+                .cloned()
+                .collect()
+        };
+        if missing.is_empty() {
+            return;
+        }
+        let found = match broadcaster_helix(&*self.plugin_manager.credentials_repo).await {
+            Ok(helix) => helix.client.fetch_account_created(&missing).await,
+            Err(e) => Err(e),
+        };
+        match found {
+            Ok(found) => {
+                let mut state = self.state.lock();
+                if state.account_created.len() > MAX_CACHED_ACCOUNTS {
+                    state.account_created.clear();
+                    state.unknown_accounts.clear();
+                }
+                for id in missing {
+                    if !found.contains_key(&id) {
+                        state.unknown_accounts.insert(id);
+                    }
+                }
+                state.account_created.extend(found);
+            }
+            Err(e) => debug!("[Protection] account age lookup failed: {:?}", e),
+        }
+    }
+
+    /// Remembers a chatter's bot score for the likely-bot check.
+    pub fn note_bot_score(&self, platform_user_id: &str, score: f32) {
+        let mut state = self.state.lock();
+        if state.bot_scores.len() > MAX_CACHED_ACCOUNTS {
+            state.bot_scores.clear();
+        }
+        state.bot_scores.insert(platform_user_id.to_string(), score);
+    }
+
+    /// Turns Shield Mode on or off by hand.
+    pub async fn set_shield_mode(&self, active: bool) -> Result<(), Error> {
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await?;
+        helix.client.set_shield_mode(&helix.broadcaster_id, &helix.broadcaster_id, active).await?;
+        info!("[Protection] Shield Mode {}", if active { "on" } else { "off" });
+        Ok(())
+    }
+
+    /// Locks chat down by hand; fails if a lockdown is already running.
+    pub async fn start_lockdown(&self, reason: Option<&str>, by: &str) -> Result<ProtectionIncident, Error> {
+        let details = match reason.map(str::trim).filter(|r| !r.is_empty()) {
+            Some(reason) => format!("{} (by {})", reason, by),
+            None => format!("started by {}", by),
+        };
+        self.lock_down(IncidentTrigger::Manual, details).await
+    }
+
+    /// Lifts the running lockdown by hand.
+    pub async fn end_lockdown(&self, by: &str) -> Result<ProtectionIncident, Error> {
+        self.lift(by).await
+    }
+
+    async fn lock_down(&self, trigger: IncidentTrigger, details: String) -> Result<ProtectionIncident, Error> {
+        let _guard = self.transition.lock().await;
+        if self.state.lock().lockdown.is_some() {
+            return Err(Error::Parse("Chat is already locked down".into()));
+        }
+
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await?;
+        let (broadcaster_id, client) = (&helix.broadcaster_id, &helix.client);
+        let mut actions = Vec::new();
+
+        let follower_minutes = self.settings.get_i64("protection.follower_only_minutes").unwrap_or(0).max(0) as u32;
+        let slow_secs = self.settings.get_i64("protection.slow_mode_secs").unwrap_or(0).max(0) as u32;
+        let followers_only = self.settings.get_bool("protection.followers_only").unwrap_or(true);
+        let mut previous = None;
+        if followers_only || slow_secs > 0 {
+            match client.fetch_chat_settings(broadcaster_id, broadcaster_id).await {
+                Ok(current) => {
+                    let mut locked = current.clone();
+                    if followers_only {
+                        locked.follower_mode = true;
+                        locked.follower_mode_duration =
+                            Some(follower_minutes.max(current.follower_mode_duration.filter(|_| current.follower_mode).unwrap_or(0)));
+                        actions.push(format!("followers_only:{}m", locked.follower_mode_duration.unwrap_or(0)));
+                    }
+                    if slow_secs > 0 {
+                        locked.slow_mode = true;
+                        locked.slow_mode_wait_time =
+                            Some(slow_secs.max(current.slow_mode_wait_time.filter(|_| current.slow_mode).unwrap_or(0)));
+                        actions.push(format!("slow_mode:{}s", locked.slow_mode_wait_time.unwrap_or(0)));
+                    }
+                    if locked != current {
+                        match client.update_chat_settings(broadcaster_id, broadcaster_id, &locked).await {
+                            Ok(()) => previous = Some(current),
+                            Err(e) => {
+                                warn!("[Protection] could not change chat settings: {:?}", e);
+                                actions.clear();
+                            }
+                        }
+                    }
+                }
+                Err(e) => warn!("[Protection] could not read chat settings: {:?}", e),
+            }
+        }
+
+        let mut enabled_shield = false;
+        if self.settings.get_bool("protection.shield_mode").unwrap_or(false) {
+            match client.fetch_shield_mode(broadcaster_id, broadcaster_id).await {
+                Ok(true) => actions.push("shield_mode".to_string()),
+                _ => match client.set_shield_mode(broadcaster_id, broadcaster_id, true).await {
+                    Ok(()) => {
+                        enabled_shield = true;
+                        actions.push("shield_mode".to_string());
+                    }
+                    Err(e) => warn!("[Protection] could not enable Shield Mode: {:?}", e),
+                },
+            }
+        }
+
+        let now = Utc::now();
+        let incident = ProtectionIncident {
+            incident_id: Uuid::new_v4(),
+            channel: helix.login.clone(),
+            trigger,
+            details,
+            actions,
+            messages: self.state.lock().window.iter().cloned().collect(),
+            started_at: now,
+            ended_at: None,
+            ended_by: None,
+        };
+        if let Err(e) = self.repo.create_incident(&incident).await {
+            warn!("[Protection] could not store incident: {:?}", e);
+        }
+        self.state.lock().lockdown = Some(Lockdown {
+            incident: incident.clone(),
+            previous,
+            enabled_shield,
+            last_spike: now,
+            last_flush: now,
+        });
+        info!("[Protection] lockdown in #{}: {} ({})", incident.channel, incident.details, incident.actions.join(", "));
+
+        let tr = |key: &str, args: &[(&str, &str)]| self.text(&incident.channel, key, args);
+        let what = describe_actions(&incident.actions, tr);
+        let text = if what.is_empty() {
+            tr("protection.triggered", &[("details", &incident.details)])
+        } else {
+            tr("protection.enabled", &[("actions", &what)])
+        };
+        self.announce(&incident, "started", &text).await;
+        Ok(incident)
+    }
+
+    async fn lift(&self, by: &str) -> Result<ProtectionIncident, Error> {
+        let _guard = self.transition.lock().await;
+        let lockdown = self.state.lock().lockdown.take()
+            .ok_or_else(|| Error::NotFound("Chat is not locked down".into()))?;
+        let Lockdown { mut incident, previous, enabled_shield, .. } = lockdown;
+
+        let helix = broadcaster_helix(&*self.plugin_manager.credentials_repo).await;
+        match &helix {
+            Ok(helix) => {
+                if let Some(previous) = &previous {
+                    if let Err(e) = helix.client.update_chat_settings(&helix.broadcaster_id, &helix.broadcaster_id, previous).await {
+                        warn!("[Protection] could not restore chat settings: {:?}", e);
+                    }
+                }
+                if enabled_shield {
+                    if let Err(e) = helix.client.set_shield_mode(&helix.broadcaster_id, &helix.broadcaster_id, false).await {
+                        warn!("[Protection] could not disable Shield Mode: {:?}", e);
+                    }
+                }
+            }
+            Err(e) => warn!("[Protection] could not restore chat: {:?}", e),
+        }
+
+        incident.ended_at = Some(Utc::now());
+        incident.ended_by = Some(by.to_string());
+        self.repo.update_incident(&incident).await?;
+        info!("[Protection] lockdown in #{} lifted by {}", incident.channel, by);
+        let text = self.text(&incident.channel, "protection.lifted", &[]);
+        self.announce(&incident, "ended", &text).await;
+        Ok(incident)
+    }
+
+    /// Saves the messages captured so far by the running lockdown.
+    async fn flush(&self) {
+        let incident = {
+            let mut state = self.state.lock();
+            let Some(lockdown) = state.lockdown.as_mut() else { return };
+            lockdown.last_flush = Utc::now();
+            lockdown.incident.clone()
+        };
+        if let Err(e) = self.repo.update_incident(&incident).await {
+            warn!("[Protection] could not save incident {}: {:?}", incident.incident_id, e);
+        }
+    }
+
+    pub async fn status(&self) -> ProtectionStatus {
+        let shield_mode = match broadcaster_helix(&*self.plugin_manager.credentials_repo).await {
+            Ok(helix) => helix.client.fetch_shield_mode(&helix.broadcaster_id, &helix.broadcaster_id).await.ok(),
+            Err(_) => None,
+        };
+        let state = self.state.lock();
+        ProtectionStatus {
+            auto_enabled: self.settings.get_bool("protection.enabled").unwrap_or(false),
+            shield_mode,
+            channel: self.channel.get(),
+            active_incident: state.lockdown.as_ref().map(|l| ProtectionIncident {
+                messages: Vec::new(),
+                ..l.incident.clone()
+            }),
+            window_messages: state.window.len(),
+        }
+    }
+
+    /// Newest first, without captured messages.
+    pub async fn list_incidents(&self, limit: i64) -> Result<Vec<ProtectionIncident>, Error> {
+        self.repo.list_incidents(limit).await
+    }
+
+    pub async fn get_incident(&self, incident_id: Uuid) -> Result<ProtectionIncident, Error> {
+        let running = self.state.lock().lockdown.as_ref()
+            .filter(|l| l.incident.incident_id == incident_id)
+            .map(|l| l.incident.clone());
+        if let Some(incident) = running {
+            return Ok(incident);
+        }
+        self.repo.get_incident(incident_id).await?
+            .ok_or_else(|| Error::NotFound(format!("No incident {}", incident_id)))
+    }
+
+    /// Chat text in the channel's language.
+    fn text(&self, channel: &str, key: &str, args: &[(&str, &str)]) -> String {
+        i18n::text_for(self.plugin_manager.localizer.as_deref(), "twitch-irc", channel, key, args)
+    }
+
+    async fn announce(&self, incident: &ProtectionIncident, event: &str, text: &str) {
+        if self.settings.get_bool("protection.announce").unwrap_or(true) {
+            let sender = MessageSender::new(
+                self.plugin_manager.credentials_repo.clone(),
+                self.plugin_manager.platform_manager.clone(),
+            );
+            if let Err(e) = sender.send_twitch_message(&incident.channel, text, None, Uuid::nil()).await {
+                warn!("[Protection] could not send to #{}: {:?}", incident.channel, e);
+            }
+        }
+
+        let summary = ProtectionIncident { messages: Vec::new(), ..incident.clone() };
+        self.plugin_manager.broadcast(
+            PluginStreamResponse {
+                payload: Some(RespPayload::GameEvent(GameEvent {
+                    name: "protection".to_string(),
+                    json: json!({ "event": event, "incident": summary, "text": text }).to_string(),
+                })),
+            },
+            None,
+        ).await;
+    }
+}
+
+/// "follower-only mode (10m) and slow mode (10s)" from the recorded actions.
+fn describe_actions(actions: &[String], tr: impl Fn(&str, &[(&str, &str)]) -> String) -> String {
+    let parts: Vec<String> = actions.iter().map(|a| match a.split_once(':') {
+        Some(("followers_only", v)) => tr("protection.action.followers_only", &[("value", v)]),
+        Some(("slow_mode", v)) => tr("protection.action.slow_mode", &[("value", v)]),
+        _ if a == "shield_mode" => tr("protection.action.shield_mode", &[]),
+        _ => a.clone(),
+    }).collect();
+    match parts.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => tr("protection.action.list", &[("first", &rest.join(", ")), ("last", last)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(user: &str, text: &str, sent_at: DateTime<Utc>) -> CapturedMessage {
+        CapturedMessage {
+            platform_user_id: user.to_string(),
+            username: user.to_string(),
+            text: text.to_string(),
+            sent_at,
+        }
+    }
+
+    #[test]
+    fn test_copies_from_a_second_account_count_once() {
+        let now = Utc::now();
+        let lockdown = Some(Lockdown {
+            incident: ProtectionIncident {
+                incident_id: Uuid::new_v4(),
+                channel: "maowcaster".to_string(),
+                trigger: IncidentTrigger::Manual,
+                details: String::new(),
+                actions: Vec::new(),
+                messages: Vec::new(),
+                started_at: now,
+                ended_at: None,
+                ended_by: None,
+            },
+            previous: None,
+            enabled_shield: false,
+            last_spike: now,
+            last_flush: now,
+        });
+        let mut state = ProtectionState { lockdown, ..Default::default() };
+
+        // The bot and the broadcaster account both receive each line
+        for (id, user, text) in [("m1", "111", "hi"), ("m2", "222", "hello")] {
+            state.observe(id, message(user, text, now));
+            state.observe(id, message(user, text, now));
+        }
+        assert_eq!(state.window.len(), 2);
+        assert_eq!(state.lockdown.as_ref().unwrap().incident.messages.len(), 2);
+
+        let thresholds = SpikeThresholds {
+            window_secs: 1,
+            messages_per_sec: 2,
+            identical_messages: 0,
+            new_account_days: 0,
+            new_account_percent: 0,
+            min_chatters: 0,
+            likely_bot_percent: 0,
+            likely_bot_score: 0.0,
+        };
+        let window = state.window.make_contiguous();
+        assert!(detect_spike(window, &HashMap::new(), &HashMap::new(), &thresholds, now).is_none());
+    }
+}
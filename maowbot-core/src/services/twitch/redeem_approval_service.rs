@@ -0,0 +1,220 @@
+// File: maowbot-core/src/services/twitch/redeem_approval_service.rs
+//
+// Approval queue for redeems with `requires_approval` set, meant for
+// disruptive effects such as OSC toggles. Their redemptions don't run when
+// they arrive but wait here until the streamer approves or denies them from
+// the GUI, TUI or gRPC. Denied redemptions, and ones nobody decides on within
+// `redeems.approval_timeout_seconds`, are cancelled on Twitch, which refunds
+// the points. Every change goes out as `BotEvent::RedeemApproval`.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{Duration, Utc};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::redeem_approval::{ApprovalStatus, RedeemApproval};
+use maowbot_common::models::Redeem;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, RedeemApprovalRepository};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::twitch::requests::channel_points::Redemption;
+use crate::services::twitch::broadcaster_helix;
+use crate::services::twitch::redeem_service::RedeemService;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// How often pending redemptions are checked against the timeout.
+const EXPIRY_CHECK: StdDuration = StdDuration::from_secs(15);
+
+pub struct RedeemApprovalService {
+    repo: Arc<dyn RedeemApprovalRepository + Send + Sync>,
+    redeem_service: Arc<RedeemService>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+}
+
+impl RedeemApprovalService {
+    /// Also registers itself with `redeem_service`, which hands it every
+    /// redemption that needs approval.
+    pub fn new(
+        repo: Arc<dyn RedeemApprovalRepository + Send + Sync>,
+        redeem_service: Arc<RedeemService>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Arc<Self> {
+        let service = Arc::new(Self {
+            repo,
+            redeem_service: redeem_service.clone(),
+            credentials_repo,
+            event_bus,
+            settings,
+        });
+        redeem_service.set_approval_service(&service);
+        service
+    }
+
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(EXPIRY_CHECK) => {
+                        if let Err(e) = service.expire_stale().await {
+                            warn!("[RedeemApprovals] checking for expired redemptions failed: {:?}", e);
+                        }
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[RedeemApprovals] loop stopped");
+        });
+    }
+
+    /// Queues a redemption of `rd` instead of running it.
+    pub async fn enqueue(
+        &self,
+        rd: &Redeem,
+        user_id: Uuid,
+        channel: &str,
+        redemption: &Redemption,
+    ) -> Result<RedeemApproval, Error> {
+        let approval = RedeemApproval {
+            approval_id: Uuid::new_v4(),
+            redeem_id: rd.redeem_id,
+            reward_name: rd.reward_name.clone(),
+            platform: rd.platform.clone(),
+            channel: channel.to_string(),
+            user_id,
+            user_name: redemption.user_name.clone()
+                .or_else(|| redemption.user_login.clone())
+                .unwrap_or_else(|| redemption.user_id.clone()),
+            user_input: redemption.user_input.clone(),
+            redemption: serde_json::to_value(redemption)?,
+            status: ApprovalStatus::Pending,
+            requested_at: Utc::now(),
+            decided_at: None,
+            decided_by: None,
+        };
+        self.repo.create_approval(&approval).await?;
+        info!("[RedeemApprovals] '{}' from {} is waiting for approval", approval.reward_name, approval.user_name);
+        self.publish(&approval).await;
+        Ok(approval)
+    }
+
+    /// Oldest first.
+    pub async fn list_pending(&self) -> Result<Vec<RedeemApproval>, Error> {
+        self.repo.list_pending().await
+    }
+
+    /// Runs the queued redemption.
+    pub async fn approve(&self, approval_id: Uuid, decided_by: &str) -> Result<RedeemApproval, Error> {
+        let approval = self.decide(approval_id, ApprovalStatus::Approved, Some(decided_by)).await?;
+        let redemption: Redemption = serde_json::from_value(approval.redemption.clone())?;
+        let channel = redemption.broadcaster_login.as_deref().unwrap_or(&approval.channel);
+        let rd = self.redeem_service.workspace_repos(&approval.platform, channel)
+            .redeems
+            .get_redeem_by_id(approval.redeem_id).await?
+            .ok_or_else(|| Error::NotFound(format!("Redeem '{}' no longer exists", approval.reward_name)))?;
+        self.redeem_service
+            .run_redeem(&rd, approval.user_id, &approval.channel, &redemption)
+            .await?;
+        info!("[RedeemApprovals] {} approved '{}' from {}", decided_by, approval.reward_name, approval.user_name);
+        Ok(approval)
+    }
+
+    /// Drops the queued redemption and refunds it.
+    pub async fn deny(&self, approval_id: Uuid, decided_by: &str) -> Result<RedeemApproval, Error> {
+        let approval = self.decide(approval_id, ApprovalStatus::Denied, Some(decided_by)).await?;
+        info!("[RedeemApprovals] {} denied '{}' from {}", decided_by, approval.reward_name, approval.user_name);
+        self.refund(&approval).await;
+        Ok(approval)
+    }
+
+    /// Expires and refunds redemptions older than the approval timeout.
+    async fn expire_stale(&self) -> Result<(), Error> {
+        let timeout = self.settings.get_u64("redeems.approval_timeout_seconds").unwrap_or(300);
+        if timeout == 0 {
+            return Ok(());
+        }
+        let cutoff = Utc::now() - Duration::seconds(timeout as i64);
+        for stale in self.repo.list_pending_before(cutoff).await? {
+            match self.decide(stale.approval_id, ApprovalStatus::Expired, None).await {
+                Ok(approval) => {
+                    info!("[RedeemApprovals] '{}' from {} expired", approval.reward_name, approval.user_name);
+                    self.refund(&approval).await;
+                }
+                // Someone decided on it in the meantime
+                Err(Error::ValidationError(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves a pending approval to `status` and tells everyone, or explains
+    /// why it can't be.
+    async fn decide(
+        &self,
+        approval_id: Uuid,
+        status: ApprovalStatus,
+        decided_by: Option<&str>,
+    ) -> Result<RedeemApproval, Error> {
+        let mut approval = self.repo.get_approval(approval_id).await?
+            .ok_or_else(|| Error::NotFound(format!("No queued redemption {}", approval_id)))?;
+        let now = Utc::now();
+        if !self.repo.decide(approval_id, status, decided_by, now).await? {
+            let current = self.repo.get_approval(approval_id).await?.map_or(approval.status, |a| a.status);
+            return Err(Error::ValidationError(format!(
+                "'{}' from {} was already {}", approval.reward_name, approval.user_name, current
+            )));
+        }
+        approval.status = status;
+        approval.decided_at = Some(now);
+        approval.decided_by = decided_by.map(String::from);
+        self.publish(&approval).await;
+        Ok(approval)
+    }
+
+    /// Cancels the redemption on Twitch, which gives the points back. Only
+    /// works for rewards that don't skip Twitch's request queue.
+    async fn refund(&self, approval: &RedeemApproval) {
+        let redemption: Redemption = match serde_json::from_value(approval.redemption.clone()) {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("[RedeemApprovals] can't read redemption {} to refund it: {}", approval.approval_id, e);
+                return;
+            }
+        };
+        let result = match broadcaster_helix(&*self.credentials_repo).await {
+            Ok(helix) => helix.client
+                .update_redemption_status(&helix.broadcaster_id, &redemption.reward.id, &[&redemption.id], "CANCELED")
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!("[RedeemApprovals] could not refund '{}' for {}: {:?}", approval.reward_name, approval.user_name, e);
+        }
+    }
+
+    async fn publish(&self, approval: &RedeemApproval) {
+        self.event_bus.publish(BotEvent::RedeemApproval {
+            approval_id: approval.approval_id,
+            status: approval.status,
+            reward_name: approval.reward_name.clone(),
+            channel: approval.channel.clone(),
+            user: approval.user_name.clone(),
+            user_input: approval.user_input.clone(),
+            decided_by: approval.decided_by.clone(),
+            timestamp: Utc::now(),
+        }).await;
+    }
+}
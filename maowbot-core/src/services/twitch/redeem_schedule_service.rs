@@ -0,0 +1,557 @@
+// File: maowbot-core/src/services/twitch/redeem_schedule_service.rs
+//
+// Applies redeem schedules: rules that enable, disable or reprice a channel
+// point reward during a weekly time window, while the stream is in a given
+// category, or while a hype train runs. A redeem's own cost and availability
+// are its base; every minute (and whenever the category or hype train
+// changes) the schedules that apply are layered on top and Twitch is patched
+// where the reward differs.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::redeem_schedule::{RedeemSchedule, ScheduleAction, ScheduleCondition};
+use maowbot_common::models::Redeem;
+use maowbot_common::traits::repository_traits::{CredentialsRepository, RedeemRepository, RedeemScheduleRepository};
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData};
+use crate::platforms::twitch::requests::channel_points::CustomRewardBody;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+const REDEEM_PLATFORM: &str = "twitch-eventsub";
+/// Twitch rejects rewards cheaper than one point.
+const MIN_COST: i64 = 1;
+
+/// A weekly window such as "mon-fri 18:00-22:00 Europe/Berlin". Days default
+/// to every day and the timezone to UTC; a window ending before it starts
+/// runs past midnight and belongs to the day it starts on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeWindow {
+    pub days: BTreeSet<u32>,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub timezone: Tz,
+}
+
+fn parse_weekday(s: &str) -> Result<u32, Error> {
+    let day: Weekday = s.parse()
+        .map_err(|_| Error::Parse(format!("Unknown day '{}', expected e.g. mon or friday", s)))?;
+    Ok(day.num_days_from_monday())
+}
+
+fn parse_days(spec: &str) -> Result<BTreeSet<u32>, Error> {
+    match spec {
+        "daily" | "every" | "all" => return Ok((0..7).collect()),
+        "weekdays" => return Ok((0..5).collect()),
+        "weekends" => return Ok([5, 6].into_iter().collect()),
+        _ => {}
+    }
+    let mut days = BTreeSet::new();
+    for part in spec.split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (parse_weekday(from)?, parse_weekday(to)?);
+                // "fri-mon" wraps over the weekend
+                let mut day = from;
+                loop {
+                    days.insert(day);
+                    if day == to {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => {
+                days.insert(parse_weekday(part)?);
+            }
+        }
+    }
+    Ok(days)
+}
+
+impl TimeWindow {
+    pub fn parse(spec: &str) -> Result<Self, Error> {
+        let parts: Vec<&str> = spec.split_whitespace().collect();
+        let usage = || Error::Parse("Expected [days] HH:MM-HH:MM [timezone], e.g. mon-fri 18:00-22:00 Europe/Berlin".into());
+        let time_at = parts.iter().position(|p| p.contains(':')).ok_or_else(usage)?;
+        if time_at > 1 || parts.len() > time_at + 2 {
+            return Err(usage());
+        }
+        let (start, end) = parts[time_at].split_once('-').ok_or_else(usage)?;
+        let time = |t: &str| NaiveTime::parse_from_str(t, "%H:%M")
+            .map_err(|_| Error::Parse(format!("Invalid time '{}', expected HH:MM", t)));
+        let (start, end) = (time(start)?, time(end)?);
+        if start == end {
+            return Err(Error::Parse("A time window can't start and end at the same time".into()));
+        }
+
+        let days = match time_at {
+            0 => (0..7).collect(),
+            _ => parse_days(&parts[0].to_lowercase())?,
+        };
+        if days.is_empty() {
+            return Err(Error::Parse("No days given".into()));
+        }
+        let timezone = match parts.get(time_at + 1) {
+            None => Tz::UTC,
+            Some(name) => name.parse::<Tz>().ok()
+                .or_else(|| chrono_tz::TZ_VARIANTS.iter().find(|tz| tz.name().eq_ignore_ascii_case(name)).copied())
+                .ok_or_else(|| Error::Parse(format!("Unknown timezone '{}'", name)))?,
+        };
+        Ok(Self { days, start, end, timezone })
+    }
+
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let local = at.with_timezone(&self.timezone);
+        let time = local.time();
+        let today = local.weekday().num_days_from_monday();
+        if self.start < self.end {
+            self.days.contains(&today) && time >= self.start && time < self.end
+        } else {
+            let yesterday = (today + 6) % 7;
+            (time >= self.start && self.days.contains(&today))
+                || (time < self.end && self.days.contains(&yesterday))
+        }
+    }
+}
+
+/// What the schedules are evaluated against.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleContext {
+    pub category: Option<String>,
+    pub hype_train: bool,
+}
+
+/// Whether `schedule` applies at `now`. Schedules whose time window doesn't
+/// parse never apply.
+pub fn schedule_applies(schedule: &RedeemSchedule, now: DateTime<Utc>, ctx: &ScheduleContext) -> bool {
+    if !schedule.enabled {
+        return false;
+    }
+    match schedule.condition {
+        ScheduleCondition::Time => TimeWindow::parse(&schedule.condition_value).is_ok_and(|w| w.contains(now)),
+        ScheduleCondition::Category => ctx.category.as_deref()
+            .is_some_and(|c| c.trim().eq_ignore_ascii_case(schedule.condition_value.trim())),
+        ScheduleCondition::HypeTrain => ctx.hype_train,
+    }
+}
+
+/// A reward's cost and availability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewardState {
+    pub cost: i64,
+    pub is_enabled: bool,
+}
+
+/// Layers the applying schedules over the redeem's own cost and availability,
+/// lowest priority first.
+pub fn effective_state<'a>(
+    base: RewardState,
+    schedules: impl IntoIterator<Item = &'a RedeemSchedule>,
+) -> RewardState {
+    let mut schedules: Vec<&RedeemSchedule> = schedules.into_iter().collect();
+    schedules.sort_by_key(|s| s.priority);
+    let mut state = base;
+    for s in schedules {
+        match s.action {
+            ScheduleAction::Enable => state.is_enabled = true,
+            ScheduleAction::Disable => state.is_enabled = false,
+            ScheduleAction::SetCost => state.cost = s.amount.unwrap_or(state.cost as f64).round() as i64,
+            ScheduleAction::MultiplyCost => state.cost = (state.cost as f64 * s.amount.unwrap_or(1.0)).round() as i64,
+        }
+    }
+    state.cost = state.cost.max(MIN_COST);
+    state
+}
+
+/// A scheduled redeem and what its schedules currently make of it.
+#[derive(Debug, Clone)]
+pub struct ScheduledRedeem {
+    pub redeem: Redeem,
+    pub schedules: Vec<RedeemSchedule>,
+    /// Names of the schedules that currently apply
+    pub applying: Vec<String>,
+    pub state: RewardState,
+}
+
+#[derive(Default)]
+struct LiveState {
+    /// `None` until known
+    category: Option<String>,
+    hype_train_until: Option<DateTime<Utc>>,
+}
+
+pub struct RedeemScheduleService {
+    repo: Arc<dyn RedeemScheduleRepository>,
+    redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    live: Mutex<LiveState>,
+    /// Held while comparing and patching rewards, so a tick and an event
+    /// never patch the same reward at once
+    apply_lock: tokio::sync::Mutex<()>,
+}
+
+fn base_state(redeem: &Redeem) -> RewardState {
+    RewardState { cost: redeem.cost as i64, is_enabled: redeem.is_active }
+}
+
+impl RedeemScheduleService {
+    pub fn new(
+        repo: Arc<dyn RedeemScheduleRepository>,
+        redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            repo,
+            redeem_repo,
+            credentials_repo,
+            event_bus,
+            settings,
+            live: Mutex::new(LiveState::default()),
+            apply_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Applies the schedules every `redeems.schedule_check_seconds` and
+    /// whenever the category changes or a hype train starts or ends.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            service.refresh_category().await;
+            loop {
+                let interval = service.settings.get_u64("redeems.schedule_check_seconds").unwrap_or(60).max(15);
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => {
+                            if service.handle_event(event) {
+                                service.apply_logged().await;
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = tokio::time::sleep(StdDuration::from_secs(interval)) => service.apply_logged().await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[RedeemSchedules] loop stopped");
+        });
+    }
+
+    /// Tracks the category and hype trains; true when schedules may now differ.
+    fn handle_event(&self, event: BotEvent) -> bool {
+        let BotEvent::TwitchEventSub(data) = event else { return false };
+        let mut live = self.live.lock();
+        match data {
+            TwitchEventSubData::ChannelUpdate(update) => {
+                let changed = live.category.as_deref() != Some(update.category_name.as_str());
+                live.category = Some(update.category_name);
+                changed
+            }
+            TwitchEventSubData::ChannelHypeTrainBegin(train) => {
+                live.hype_train_until = Some(train.expires_at);
+                true
+            }
+            TwitchEventSubData::ChannelHypeTrainProgress(train) => {
+                live.hype_train_until = Some(train.expires_at);
+                false
+            }
+            TwitchEventSubData::ChannelHypeTrainEnd(_) => {
+                live.hype_train_until = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reads the category once at startup; later changes arrive as channel.update.
+    async fn refresh_category(&self) {
+        let info = match broadcaster_helix(&*self.credentials_repo).await {
+            Ok(helix) => helix.client.get_channel_information(&helix.broadcaster_id).await,
+            Err(e) => Err(e),
+        };
+        match info {
+            Ok(Some(info)) => self.live.lock().category = Some(info.game_name),
+            Ok(None) => {}
+            Err(e) => debug!("[RedeemSchedules] could not read the stream category: {:?}", e),
+        }
+    }
+
+    fn context(&self) -> ScheduleContext {
+        let live = self.live.lock();
+        ScheduleContext {
+            category: live.category.clone().filter(|c| !c.is_empty()),
+            // An end event can be missed; the expiry bounds the train either way
+            hype_train: live.hype_train_until.is_some_and(|until| until > Utc::now()),
+        }
+    }
+
+    async fn apply_logged(&self) {
+        match self.apply().await {
+            Ok(0) => {}
+            Ok(n) => info!("[RedeemSchedules] updated {} reward(s) on Twitch", n),
+            Err(e) => debug!("[RedeemSchedules] apply failed: {:?}", e),
+        }
+    }
+
+    pub async fn list_schedules(&self) -> Result<Vec<RedeemSchedule>, Error> {
+        self.repo.list_schedules().await
+    }
+
+    /// Every redeem with schedules, with what they currently make of it.
+    pub async fn status(&self) -> Result<Vec<ScheduledRedeem>, Error> {
+        let mut by_redeem: HashMap<Uuid, Vec<RedeemSchedule>> = HashMap::new();
+        for s in self.repo.list_schedules().await? {
+            by_redeem.entry(s.redeem_id).or_default().push(s);
+        }
+        if by_redeem.is_empty() {
+            return Ok(Vec::new());
+        }
+        let now = Utc::now();
+        let ctx = self.context();
+        let mut scheduled: Vec<ScheduledRedeem> = self.redeem_repo.list_redeems(REDEEM_PLATFORM).await?
+            .into_iter()
+            .filter_map(|redeem| {
+                let schedules = by_redeem.remove(&redeem.redeem_id)?;
+                let applying: Vec<&RedeemSchedule> = schedules.iter().filter(|s| schedule_applies(s, now, &ctx)).collect();
+                let state = effective_state(base_state(&redeem), applying.iter().copied());
+                let applying = applying.iter().map(|s| s.name.clone()).collect();
+                Some(ScheduledRedeem { redeem, schedules, applying, state })
+            })
+            .collect();
+        scheduled.sort_by_key(|a| a.redeem.reward_name.to_lowercase());
+        Ok(scheduled)
+    }
+
+    /// Patches every scheduled reward that differs on Twitch; returns how many were patched.
+    pub async fn apply(&self) -> Result<usize, Error> {
+        let _guard = self.apply_lock.lock().await;
+        let scheduled = self.status().await?;
+        if scheduled.is_empty() {
+            return Ok(0);
+        }
+        let helix = broadcaster_helix(&*self.credentials_repo).await?;
+        // Only rewards the bot created can be edited, so only those are fetched
+        let rewards = helix.client.get_custom_rewards(&helix.broadcaster_id, None, true).await?;
+
+        let mut patched = 0;
+        for s in scheduled {
+            let Some(reward) = rewards.iter().find(|r| r.id == s.redeem.reward_id) else {
+                debug!("[RedeemSchedules] '{}' isn't a bot-managed reward on Twitch; skipped", s.redeem.reward_name);
+                continue;
+            };
+            let body = CustomRewardBody {
+                cost: Some(s.state.cost as u64).filter(|c| *c != reward.cost),
+                is_enabled: Some(s.state.is_enabled).filter(|e| *e != reward.is_enabled),
+                ..Default::default()
+            };
+            if body.cost.is_none() && body.is_enabled.is_none() {
+                continue;
+            }
+            match helix.client.update_custom_reward(&helix.broadcaster_id, &reward.id, &body).await {
+                Ok(_) => {
+                    debug!(
+                        "[RedeemSchedules] '{}' => cost {}, enabled {} ({})",
+                        s.redeem.reward_name, s.state.cost, s.state.is_enabled,
+                        if s.applying.is_empty() { "base".to_string() } else { s.applying.join(", ") }
+                    );
+                    patched += 1;
+                }
+                Err(e) => warn!("[RedeemSchedules] could not update '{}': {}", s.redeem.reward_name, e),
+            }
+        }
+        Ok(patched)
+    }
+
+    /// Adds a schedule to a bot-managed redeem and applies it right away.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_schedule(
+        &self,
+        redeem_id: Uuid,
+        name: &str,
+        condition: ScheduleCondition,
+        condition_value: &str,
+        action: ScheduleAction,
+        amount: Option<f64>,
+        priority: i32,
+    ) -> Result<RedeemSchedule, Error> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::Parse("A schedule needs a name".into()));
+        }
+        let mut redeem = self.redeem_repo.get_redeem_by_id(redeem_id).await?
+            .ok_or_else(|| Error::NotFound(format!("No redeem with id {}", redeem_id)))?;
+        if !redeem.is_managed {
+            return Err(Error::Parse(format!(
+                "'{}' wasn't created by the bot, and Twitch only lets the bot edit rewards it created",
+                redeem.reward_name
+            )));
+        }
+        let condition_value = condition_value.trim();
+        match condition {
+            ScheduleCondition::Time => {
+                TimeWindow::parse(condition_value)?;
+            }
+            ScheduleCondition::Category if condition_value.is_empty() => {
+                return Err(Error::Parse("A category schedule needs a category name".into()));
+            }
+            _ => {}
+        }
+        let amount = match action {
+            ScheduleAction::SetCost => Some(amount.filter(|a| *a >= MIN_COST as f64)
+                .ok_or_else(|| Error::Parse("A cost of at least 1 is required".into()))?.round()),
+            ScheduleAction::MultiplyCost => Some(amount.filter(|a| *a > 0.0)
+                .ok_or_else(|| Error::Parse("A positive factor is required, e.g. 2 or 0.5".into()))?),
+            ScheduleAction::Enable | ScheduleAction::Disable => None,
+        };
+        if self.repo.list_schedules().await?.iter().any(|s| s.redeem_id == redeem_id && s.name.eq_ignore_ascii_case(name)) {
+            return Err(Error::Parse(format!("'{}' already has a schedule named '{}'", redeem.reward_name, name)));
+        }
+
+        let now = Utc::now();
+        let schedule = RedeemSchedule {
+            schedule_id: Uuid::new_v4(),
+            redeem_id,
+            name: name.to_string(),
+            condition,
+            condition_value: if condition == ScheduleCondition::HypeTrain { String::new() } else { condition_value.to_string() },
+            action,
+            amount,
+            priority,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+        self.repo.create_schedule(&schedule).await?;
+        // Redeem sync leaves the cost and availability of dynamic redeems to the schedules
+        if !redeem.dynamic_pricing {
+            redeem.dynamic_pricing = true;
+            redeem.updated_at = now;
+            self.redeem_repo.update_redeem(&redeem).await?;
+        }
+        self.apply_logged().await;
+        Ok(schedule)
+    }
+
+    /// Removes a schedule; the reward returns to its own cost and availability
+    /// once no schedule applies.
+    pub async fn remove_schedule(&self, schedule_id: Uuid) -> Result<(), Error> {
+        let schedules = self.repo.list_schedules().await?;
+        let schedule = schedules.iter().find(|s| s.schedule_id == schedule_id)
+            .ok_or_else(|| Error::NotFound(format!("No schedule with id {}", schedule_id)))?;
+        self.repo.delete_schedule(schedule_id).await?;
+
+        let last = !schedules.iter().any(|s| s.redeem_id == schedule.redeem_id && s.schedule_id != schedule_id);
+        // Apply before clearing the flag, so the reward is returned to its base
+        self.apply_logged().await;
+        if last {
+            if let Some(mut redeem) = self.redeem_repo.get_redeem_by_id(schedule.redeem_id).await? {
+                self.restore_base(&redeem).await;
+                redeem.dynamic_pricing = false;
+                redeem.updated_at = Utc::now();
+                self.redeem_repo.update_redeem(&redeem).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Puts a reward whose last schedule is gone back to the redeem's own values.
+    async fn restore_base(&self, redeem: &Redeem) {
+        let body = CustomRewardBody {
+            cost: Some(redeem.cost.max(MIN_COST as i32) as u64),
+            is_enabled: Some(redeem.is_active),
+            ..Default::default()
+        };
+        let result = match broadcaster_helix(&*self.credentials_repo).await {
+            Ok(helix) => helix.client.update_custom_reward(&helix.broadcaster_id, &redeem.reward_id, &body).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!("[RedeemSchedules] could not restore '{}' on Twitch: {}", redeem.reward_name, e);
+        }
+    }
+
+    pub async fn set_schedule_enabled(&self, schedule_id: Uuid, enabled: bool) -> Result<RedeemSchedule, Error> {
+        let mut schedule = self.repo.list_schedules().await?
+            .into_iter()
+            .find(|s| s.schedule_id == schedule_id)
+            .ok_or_else(|| Error::NotFound(format!("No schedule with id {}", schedule_id)))?;
+        schedule.enabled = enabled;
+        schedule.updated_at = Utc::now();
+        self.repo.update_schedule(&schedule).await?;
+        self.apply_logged().await;
+        Ok(schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(name: &str, action: ScheduleAction, amount: Option<f64>, priority: i32) -> RedeemSchedule {
+        RedeemSchedule {
+            schedule_id: Uuid::new_v4(),
+            redeem_id: Uuid::nil(),
+            name: name.to_string(),
+            condition: ScheduleCondition::HypeTrain,
+            condition_value: String::new(),
+            action,
+            amount,
+            priority,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parses_and_matches_time_windows() {
+        let w = TimeWindow::parse("mon-fri 18:00-22:00 Europe/Berlin").unwrap();
+        assert_eq!(w.days, (0..5).collect());
+        assert_eq!(w.timezone, chrono_tz::Europe::Berlin);
+        // Wednesday 2024-01-10, 18:30 UTC is 19:30 in Berlin
+        assert!(w.contains(Utc.with_ymd_and_hms(2024, 1, 10, 18, 30, 0).unwrap()));
+        assert!(!w.contains(Utc.with_ymd_and_hms(2024, 1, 10, 21, 30, 0).unwrap()));
+        assert!(!w.contains(Utc.with_ymd_and_hms(2024, 1, 13, 18, 30, 0).unwrap()));
+
+        // Friday night into Saturday belongs to Friday
+        let late = TimeWindow::parse("fri 22:00-02:00").unwrap();
+        assert!(late.contains(Utc.with_ymd_and_hms(2024, 1, 13, 1, 0, 0).unwrap()));
+        assert!(!late.contains(Utc.with_ymd_and_hms(2024, 1, 14, 1, 0, 0).unwrap()));
+        assert_eq!(TimeWindow::parse("sat-mon 10:00-11:00").unwrap().days, [0, 5, 6].into_iter().collect());
+
+        assert!(TimeWindow::parse("18:00").is_err());
+        assert!(TimeWindow::parse("someday 18:00-19:00").is_err());
+        assert!(TimeWindow::parse("18:00-19:00 Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_layers_schedules_by_priority() {
+        let base = RewardState { cost: 100, is_enabled: false };
+        let double = schedule("double", ScheduleAction::MultiplyCost, Some(2.0), 10);
+        let fixed = schedule("fixed", ScheduleAction::SetCost, Some(300.0), 0);
+        let on = schedule("on", ScheduleAction::Enable, None, 0);
+        assert_eq!(effective_state(base, [&double, &fixed, &on]), RewardState { cost: 600, is_enabled: true });
+        assert_eq!(effective_state(base, []), base);
+        let tiny = schedule("tiny", ScheduleAction::MultiplyCost, Some(0.001), 0);
+        assert_eq!(effective_state(base, [&tiny]).cost, 1);
+    }
+}
@@ -1,11 +1,11 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, Weak};
 use chrono::{Utc};
 use uuid::Uuid;
 use tracing::{info, warn, debug};
-use sqlx::PgPool;
 use tokio::sync::RwLock;
 use maowbot_common::models::platform::{Platform, PlatformCredential};
 use maowbot_common::models::{Redeem, RedeemUsage};
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
 use maowbot_common::traits::repository_traits::{RedeemRepository, RedeemUsageRepository, CredentialsRepository, UserRepo};
 use maowbot_osc::MaowOscManager;
 use crate::Error;
@@ -13,6 +13,15 @@ use crate::services::user_service::UserService;
 use crate::platforms::manager::PlatformManager;
 use crate::platforms::twitch::requests::channel_points::Redemption;
 use crate::services::twitch::builtin_redeems;
+use crate::services::twitch::redeem_approval_service::RedeemApprovalService;
+use crate::services::workspace_router::WorkspaceRouter;
+use crate::repositories::WorkspaceRepos;
+
+/// The channel a redemption belongs to: its broadcaster's login, which EventSub
+/// always sends, else whatever the caller passed.
+fn redemption_channel<'a>(channel: &'a str, redemption: &'a Redemption) -> &'a str {
+    redemption.broadcaster_login.as_deref().filter(|l| !l.is_empty()).unwrap_or(channel)
+}
 
 /// Holds references needed in a built‑in redeem flow:
 pub struct RedeemHandlerContext<'a> {
@@ -26,9 +35,6 @@ pub struct RedeemHandlerContext<'a> {
     /// (based on `active_credential_id` fallback).
     pub active_credential: Option<PlatformCredential>,
     
-    /// Database pool for creating repositories
-    pub pool: PgPool,
-    
     /// OSC manager for toggle controls
     pub osc_manager: Arc<RwLock<Option<MaowOscManager>>>,
     
@@ -39,8 +45,12 @@ pub struct RedeemHandlerContext<'a> {
     pub redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
 }
 
+/// Runs channel point redeems. A redemption is looked up in the workspace its
+/// broadcaster's channel is routed to (see `WorkspaceRouter`); `redeem_repo` is
+/// the default workspace's.
 pub struct RedeemService {
-    pub(crate) redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
+    workspaces: Arc<WorkspaceRouter>,
+    pub redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
     usage_repo: Arc<dyn RedeemUsageRepository + Send + Sync>,
     pub user_service: Arc<UserService>,
 
@@ -49,36 +59,49 @@ pub struct RedeemService {
     /// For picking fallback credentials, we also need direct access to credentials_repo
     pub credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
     
-    /// Database pool for creating repositories
-    pub pool: PgPool,
-    
     /// OSC manager for toggle controls
     pub osc_manager: Arc<RwLock<Option<MaowOscManager>>>,
     
     /// User repository for user lookups
     pub user_repo: Arc<dyn UserRepo + Send + Sync>,
+
+    /// Where redemptions of redeems that need approval go
+    approvals: OnceLock<Weak<RedeemApprovalService>>,
 }
 
 impl RedeemService {
     pub fn new(
-        redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
+        workspaces: Arc<WorkspaceRouter>,
         usage_repo: Arc<dyn RedeemUsageRepository + Send + Sync>,
         user_service: Arc<UserService>,
         platform_manager: Arc<PlatformManager>,
-        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
-        pool: PgPool,
         osc_manager: Arc<RwLock<Option<MaowOscManager>>>,
         user_repo: Arc<dyn UserRepo + Send + Sync>,
     ) -> Self {
+        let redeem_repo = workspaces.repos(DEFAULT_WORKSPACE_ID).redeems;
+        let credentials_repo = workspaces.credentials();
         Self {
+            workspaces,
             redeem_repo,
             usage_repo,
             user_service,
             platform_manager,
             credentials_repo,
-            pool,
             osc_manager,
             user_repo,
+            approvals: OnceLock::new(),
+        }
+    }
+
+    /// Redeems, credentials and config of the workspace `channel` is routed to.
+    pub fn workspace_repos(&self, platform: &str, channel: &str) -> WorkspaceRepos {
+        self.workspaces.repos_for(platform, channel)
+    }
+
+    /// Called by the RedeemApprovalService when it's created.
+    pub fn set_approval_service(&self, approvals: &Arc<RedeemApprovalService>) {
+        if self.approvals.set(Arc::downgrade(approvals)).is_err() {
+            warn!("Redeem approval service registered twice; keeping the first");
         }
     }
     
@@ -127,7 +150,8 @@ impl RedeemService {
         channel: &str,
         redemption: &Redemption,
     ) -> Result<(), Error> {
-        let rd_opt = self.redeem_repo
+        let rd_opt = self.workspace_repos(platform, redemption_channel(channel, redemption))
+            .redeems
             .get_redeem_by_reward_id(platform, reward_id)
             .await?;
         let rd = match rd_opt {
@@ -143,6 +167,31 @@ impl RedeemService {
             return Ok(());
         }
 
+        if rd.requires_approval {
+            match self.approvals.get().and_then(Weak::upgrade) {
+                Some(approvals) => {
+                    approvals.enqueue(&rd, user_id, channel, redemption).await?;
+                }
+                None => warn!(
+                    "Redeem '{}' needs approval but the approval queue isn't running => leaving it in Twitch's queue",
+                    rd.reward_name
+                ),
+            }
+            return Ok(());
+        }
+
+        self.run_redeem(&rd, user_id, channel, redemption).await
+    }
+
+    /// Logs the usage and runs the redeem's handler, right away or once an
+    /// approval goes through.
+    pub async fn run_redeem(
+        &self,
+        rd: &Redeem,
+        user_id: Uuid,
+        channel: &str,
+        redemption: &Redemption,
+    ) -> Result<(), Error> {
         // Log usage
         let usage = RedeemUsage {
             usage_id: Uuid::new_v4(),
@@ -155,17 +204,17 @@ impl RedeemService {
         self.usage_repo.insert_usage(&usage).await?;
 
         // Decide which credential actually processes it => check rd.active_credential_id
-        let chosen_credential = self.pick_active_redeem_credential(&rd, user_id).await?;
+        let scoped = self.workspace_repos(&rd.platform, redemption_channel(channel, redemption));
+        let chosen_credential = self.pick_active_redeem_credential(rd, user_id, &scoped).await?;
 
         // Build the handler context
         let ctx = RedeemHandlerContext {
             helix_client: self.get_helix_client_for_credential(&chosen_credential).await,
             redeem_service: self,
             active_credential: chosen_credential,
-            pool: self.pool.clone(),
             osc_manager: self.osc_manager.clone(),
             user_repo: self.user_repo.clone(),
-            redeem_repo: self.redeem_repo.clone(),
+            redeem_repo: scoped.redeems,
         };
 
         // If plugin_name is “builtin”, handle:
@@ -192,14 +241,17 @@ impl RedeemService {
     ///     - then broadcaster,
     ///     - else the same user’s own credential,
     ///     - else None if truly no credential at all.
+    ///
+    /// Only credentials of the redeem's workspace (`scoped`) are considered.
     async fn pick_active_redeem_credential(
         &self,
         rd: &Redeem,
-        redeeming_user_id: Uuid
+        redeeming_user_id: Uuid,
+        scoped: &WorkspaceRepos,
     ) -> Result<Option<PlatformCredential>, Error> {
         // step 1
         if let Some(cid) = rd.active_credential_id {
-            if let Ok(Some(c)) = self.credentials_repo.get_credential_by_id(cid).await {
+            if let Ok(Some(c)) = scoped.credentials.get_credential_by_id(cid).await {
                 if c.platform == Platform::TwitchIRC || c.platform == Platform::Twitch {
                     return Ok(Some(c));
                 }
@@ -207,7 +259,7 @@ impl RedeemService {
         }
 
         // step 2 (fallback chain)
-        let all_irc = self.credentials_repo
+        let all_irc = scoped.credentials
             .list_credentials_for_platform(&Platform::TwitchIRC)
             .await?;
         // first: any bot?
@@ -295,6 +347,7 @@ impl RedeemService {
             command_name: None,
             is_input_required: false,
             redeem_prompt_text: None,
+            requires_approval: false,
         };
         self.redeem_repo.create_redeem(&rd).await?;
         Ok(rd)
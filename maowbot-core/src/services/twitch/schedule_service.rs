@@ -0,0 +1,321 @@
+// File: maowbot-core/src/services/twitch/schedule_service.rs
+//
+// The broadcaster's Twitch stream schedule. Segments are cached for a few
+// minutes so `!schedule` doesn't hit Helix on every use, and edits made
+// through the service refresh the cache. Every half minute the loop looks for
+// a segment starting within `twitch.schedule.starting_soon_minutes`; if the
+// stream isn't live yet it switches OBS to the "starting soon" scene, posts
+// the announcement to chat and publishes `BotEvent::TwitchScheduleStartingSoon`,
+// once per occurrence.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::i18n::render;
+use crate::platforms::manager::PlatformManager;
+use crate::platforms::twitch::requests::schedule::{NewScheduleSegment, ScheduleSegment, ScheduleSegmentUpdate};
+use crate::services::message_sender::MessageSender;
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// How long fetched segments are served before asking Twitch again.
+const CACHE_TTL: StdDuration = StdDuration::from_secs(300);
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(30);
+
+/// Changes to make to a segment; None leaves a field as it is.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleEdit {
+    pub start_time: Option<DateTime<Utc>>,
+    pub duration_minutes: Option<u32>,
+    pub title: Option<String>,
+    /// Category name; resolved to a Twitch category id
+    pub category: Option<String>,
+    /// Cancel (or restore) just the next occurrence
+    pub canceled: Option<bool>,
+}
+
+/// Segments that begin within `lead` of `now` and haven't been announced.
+/// Canceled occurrences are never due.
+pub fn due_segments<'a>(
+    segments: &'a [ScheduleSegment],
+    now: DateTime<Utc>,
+    lead: Duration,
+    announced: &HashSet<(String, DateTime<Utc>)>,
+) -> Vec<&'a ScheduleSegment> {
+    segments.iter()
+        .filter(|s| s.canceled_until.is_none())
+        .filter(|s| s.start_time > now && s.start_time - lead <= now)
+        .filter(|s| !announced.contains(&(s.id.clone(), s.start_time)))
+        .collect()
+}
+
+pub struct ScheduleService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    platform_manager: Arc<PlatformManager>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    cache: Mutex<Option<(Instant, Vec<ScheduleSegment>)>>,
+    /// (segment id, start time) of occurrences already announced
+    announced: Mutex<HashSet<(String, DateTime<Utc>)>>,
+}
+
+impl ScheduleService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        platform_manager: Arc<PlatformManager>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            credentials_repo,
+            platform_manager,
+            event_bus,
+            settings,
+            cache: Mutex::new(None),
+            announced: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Checks for segments starting soon while `twitch.schedule.enabled`.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                if service.settings.get_bool("twitch.schedule.enabled").unwrap_or(true) {
+                    if let Err(e) = service.check_starting_soon().await {
+                        debug!("[Schedule] starting-soon check failed: {:?}", e);
+                    }
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Schedule] loop stopped");
+        });
+    }
+
+    /// The upcoming segments, soonest first, from the cache when it's fresh.
+    pub async fn upcoming(&self) -> Result<Vec<ScheduleSegment>, Error> {
+        if let Some((at, segments)) = self.cache.lock().as_ref() {
+            if at.elapsed() < CACHE_TTL {
+                return Ok(segments.clone());
+            }
+        }
+        self.refresh().await
+    }
+
+    /// Fetches the schedule from Twitch, bypassing the cache.
+    pub async fn refresh(&self) -> Result<Vec<ScheduleSegment>, Error> {
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        let mut segments = broadcaster.client.get_channel_schedule(&broadcaster.broadcaster_id).await?;
+        segments.sort_by_key(|s| s.start_time);
+        *self.cache.lock() = Some((Instant::now(), segments.clone()));
+        Ok(segments)
+    }
+
+    fn invalidate(&self) {
+        *self.cache.lock() = None;
+    }
+
+    fn timezone(&self) -> String {
+        self.settings.get("twitch.schedule.timezone")
+            .filter(|tz| !tz.trim().is_empty())
+            .unwrap_or_else(|| "UTC".to_string())
+    }
+
+    /// Adds a segment. `timezone` defaults to `twitch.schedule.timezone`.
+    pub async fn create_segment(
+        &self,
+        start_time: DateTime<Utc>,
+        duration_minutes: u32,
+        title: &str,
+        category: Option<&str>,
+        is_recurring: bool,
+        timezone: Option<&str>,
+    ) -> Result<ScheduleSegment, Error> {
+        if !(30..=1380).contains(&duration_minutes) {
+            return Err(Error::ValidationError("Segments last between 30 and 1380 minutes".into()));
+        }
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        let category_id = match category.map(str::trim).filter(|c| !c.is_empty()) {
+            Some(name) => Some(self.category_id(&broadcaster.client, name).await?),
+            None => None,
+        };
+        let segment = NewScheduleSegment {
+            start_time,
+            timezone: timezone.map(str::to_string).unwrap_or_else(|| self.timezone()),
+            duration: duration_minutes.to_string(),
+            is_recurring,
+            category_id,
+            title: title.to_string(),
+        };
+        let created = broadcaster.client.create_schedule_segment(&broadcaster.broadcaster_id, &segment).await?;
+        info!("[Schedule] added '{}' at {}", created.title, created.start_time);
+        self.invalidate();
+        Ok(created)
+    }
+
+    pub async fn update_segment(&self, segment_id: &str, edit: ScheduleEdit) -> Result<ScheduleSegment, Error> {
+        if edit.duration_minutes.is_some_and(|d| !(30..=1380).contains(&d)) {
+            return Err(Error::ValidationError("Segments last between 30 and 1380 minutes".into()));
+        }
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        let category_id = match edit.category.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            Some(name) => Some(self.category_id(&broadcaster.client, name).await?),
+            None => None,
+        };
+        let update = ScheduleSegmentUpdate {
+            timezone: edit.start_time.map(|_| self.timezone()),
+            start_time: edit.start_time,
+            duration: edit.duration_minutes.map(|d| d.to_string()),
+            category_id,
+            title: edit.title,
+            is_canceled: edit.canceled,
+        };
+        let updated = broadcaster.client
+            .update_schedule_segment(&broadcaster.broadcaster_id, segment_id, &update)
+            .await?;
+        self.invalidate();
+        Ok(updated)
+    }
+
+    pub async fn delete_segment(&self, segment_id: &str) -> Result<(), Error> {
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        broadcaster.client.delete_schedule_segment(&broadcaster.broadcaster_id, segment_id).await?;
+        info!("[Schedule] removed segment {}", segment_id);
+        self.invalidate();
+        Ok(())
+    }
+
+    async fn category_id(
+        &self,
+        client: &crate::platforms::twitch::client::TwitchHelixClient,
+        name: &str,
+    ) -> Result<String, Error> {
+        client.fetch_game_by_name(name).await?
+            .map(|game| game.id)
+            .ok_or_else(|| Error::ValidationError(format!("No Twitch category named '{}'", name)))
+    }
+
+    async fn check_starting_soon(&self) -> Result<(), Error> {
+        let lead = self.settings.get_u64("twitch.schedule.starting_soon_minutes").unwrap_or(10);
+        if lead == 0 {
+            return Ok(());
+        }
+        let segments = self.upcoming().await?;
+        let now = Utc::now();
+        let due: Vec<ScheduleSegment> = {
+            let mut announced = self.announced.lock();
+            announced.retain(|(_, start)| *start > now - Duration::days(1));
+            due_segments(&segments, now, Duration::minutes(lead as i64), &announced)
+                .into_iter()
+                .cloned()
+                .collect()
+        };
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        let broadcaster = broadcaster_helix(&*self.credentials_repo).await?;
+        let live = broadcaster.client.fetch_live_stream(&broadcaster.broadcaster_id).await?.is_some();
+        for segment in due {
+            self.announced.lock().insert((segment.id.clone(), segment.start_time));
+            if live {
+                debug!("[Schedule] '{}' is due but the stream is already live", segment.title);
+                continue;
+            }
+            self.announce(&segment, &broadcaster.login, now).await;
+        }
+        Ok(())
+    }
+
+    async fn announce(&self, segment: &ScheduleSegment, channel: &str, now: DateTime<Utc>) {
+        let minutes = (segment.start_time - now).num_minutes().max(1);
+        let category = segment.category.as_ref().map(|c| c.name.clone()).unwrap_or_default();
+        info!("[Schedule] '{}' starts in {} minute(s)", segment.title, minutes);
+
+        let scene = self.settings.get("twitch.schedule.starting_soon_scene").unwrap_or_default();
+        if !scene.trim().is_empty() {
+            let instance = self.settings.get_u64("twitch.schedule.obs_instance").unwrap_or(1) as u32;
+            match self.platform_manager.get_obs_instance(instance).await {
+                Ok(obs) => {
+                    if let Err(e) = obs.get_client().set_current_scene(scene.trim()).await {
+                        warn!("[Schedule] OBS {} could not switch to '{}': {}", instance, scene, e);
+                    }
+                }
+                Err(e) => warn!("[Schedule] OBS {} unavailable: {:?}", instance, e),
+            }
+        }
+
+        let template = self.settings.get("twitch.schedule.starting_soon_message").unwrap_or_default();
+        if !template.trim().is_empty() {
+            let text = render(&template, &[
+                ("title", segment.title.as_str()),
+                ("category", category.as_str()),
+                ("minutes", &minutes.to_string()),
+            ]);
+            let sender = MessageSender::new(self.credentials_repo.clone(), self.platform_manager.clone());
+            if let Err(e) = sender.send_twitch_message(channel, &text, None, Uuid::nil()).await {
+                warn!("[Schedule] starting-soon announcement failed: {:?}", e);
+            }
+        }
+
+        self.event_bus.publish(BotEvent::TwitchScheduleStartingSoon {
+            channel: channel.to_string(),
+            segment_id: segment.id.clone(),
+            title: segment.title.clone(),
+            category,
+            start_time: segment.start_time,
+            timestamp: now,
+        }).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: &str, start_time: DateTime<Utc>, canceled: bool) -> ScheduleSegment {
+        ScheduleSegment {
+            id: id.to_string(),
+            start_time,
+            end_time: None,
+            title: id.to_string(),
+            canceled_until: canceled.then_some(start_time),
+            category: None,
+            is_recurring: false,
+        }
+    }
+
+    #[test]
+    fn test_segments_are_due_once_inside_the_lead_time() {
+        let now = Utc::now();
+        let segments = vec![
+            segment("soon", now + Duration::minutes(5), false),
+            segment("later", now + Duration::minutes(45), false),
+            segment("canceled", now + Duration::minutes(3), true),
+            segment("started", now - Duration::minutes(1), false),
+        ];
+        let mut announced = HashSet::new();
+        let due = due_segments(&segments, now, Duration::minutes(10), &announced);
+        assert_eq!(due.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["soon"]);
+
+        announced.insert(("soon".to_string(), segments[0].start_time));
+        assert!(due_segments(&segments, now, Duration::minutes(10), &announced).is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+// File: maowbot-core/src/services/twitch/scope_check.rs
+//
+// Scope verification for stored Twitch tokens. Twitch keeps adding scopes
+// that endpoints require, and a token granted before the bot asked for one
+// fails those calls with a bare 401. At startup (and on `account scopes
+// --recheck`) every Twitch credential is checked against the scopes its
+// login asks for today; features that need a missing scope are marked
+// degraded, and the owner is told which account to log in with again.
+
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::twitch::auth::HELIX_SCOPES;
+use crate::platforms::twitch::client::TwitchHelixClient;
+use crate::platforms::twitch_irc::auth::IRC_SCOPES;
+use crate::Error;
+
+const VALIDATE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A bot feature and the scopes it can't work without.
+#[derive(Debug)]
+pub struct ScopedFeature {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// `Twitch` covers both the Helix and EventSub logins, which share a token
+    pub platform: Platform,
+    pub scopes: &'static [&'static str],
+}
+
+impl ScopedFeature {
+    fn applies_to(&self, platform: &Platform) -> bool {
+        match self.platform {
+            Platform::Twitch => matches!(platform, Platform::Twitch | Platform::TwitchEventSub),
+            ref p => p == platform,
+        }
+    }
+}
+
+pub const TWITCH_FEATURES: &[ScopedFeature] = &[
+    ScopedFeature {
+        name: "chat",
+        description: "Reading and sending chat over IRC",
+        platform: Platform::TwitchIRC,
+        scopes: &["chat:read", "chat:edit"],
+    },
+    ScopedFeature {
+        name: "chat_events",
+        description: "Chat notifications over EventSub",
+        platform: Platform::Twitch,
+        scopes: &["user:read:chat"],
+    },
+    ScopedFeature {
+        name: "moderation",
+        description: "Bans, timeouts, message deletes and chat settings",
+        platform: Platform::Twitch,
+        scopes: &[
+            "channel:moderate",
+            "moderator:manage:banned_users",
+            "moderator:manage:chat_messages",
+            "moderator:manage:chat_settings",
+            "moderator:read:unban_requests",
+        ],
+    },
+    ScopedFeature {
+        name: "shield_mode",
+        description: "Raid defense lockdowns",
+        platform: Platform::Twitch,
+        scopes: &["moderator:manage:shield_mode"],
+    },
+    ScopedFeature {
+        name: "ads",
+        description: "Ad schedule and ad break alerts",
+        platform: Platform::Twitch,
+        scopes: &["channel:read:ads"],
+    },
+    ScopedFeature {
+        name: "redeems",
+        description: "Managing channel point rewards and redemptions",
+        platform: Platform::Twitch,
+        scopes: &["channel:manage:redemptions"],
+    },
+    ScopedFeature {
+        name: "follows",
+        description: "Follow alerts",
+        platform: Platform::Twitch,
+        scopes: &["moderator:read:followers"],
+    },
+    ScopedFeature {
+        name: "chatters",
+        description: "Viewer presence, lurkers and watchtime",
+        platform: Platform::Twitch,
+        scopes: &["moderator:read:chatters"],
+    },
+    ScopedFeature {
+        name: "subs_and_bits",
+        description: "Subscription, gift and cheer alerts",
+        platform: Platform::Twitch,
+        scopes: &["channel:read:subscriptions", "bits:read"],
+    },
+    ScopedFeature {
+        name: "hype_train",
+        description: "Hype train alerts and schedules",
+        platform: Platform::Twitch,
+        scopes: &["channel:read:hype_train"],
+    },
+    ScopedFeature {
+        name: "shoutouts",
+        description: "Shoutout alerts",
+        platform: Platform::Twitch,
+        scopes: &["moderator:read:shoutouts"],
+    },
+    ScopedFeature {
+        name: "stream_info",
+        description: "Title, category and stream markers",
+        platform: Platform::Twitch,
+        scopes: &["channel:manage:broadcast"],
+    },
+    ScopedFeature {
+        name: "schedule",
+        description: "Editing the stream schedule",
+        platform: Platform::Twitch,
+        scopes: &["channel:manage:schedule"],
+    },
+    ScopedFeature {
+        name: "clips",
+        description: "Creating clips",
+        platform: Platform::Twitch,
+        scopes: &["clips:edit"],
+    },
+    ScopedFeature {
+        name: "whispers",
+        description: "Sending whispers",
+        platform: Platform::Twitch,
+        scopes: &["user:manage:whispers"],
+    },
+];
+
+/// The scopes a login for `platform` asks for today; empty for non-Twitch platforms.
+pub fn required_scopes(platform: &Platform) -> &'static [&'static str] {
+    match platform {
+        Platform::Twitch | Platform::TwitchEventSub => HELIX_SCOPES,
+        Platform::TwitchIRC => IRC_SCOPES,
+        _ => &[],
+    }
+}
+
+/// Scopes recorded in `additional_data` when the credential was stored
+/// ("scope" may be an array or a space-separated string).
+pub fn stored_scopes(cred: &PlatformCredential) -> Vec<String> {
+    match cred.additional_data.as_ref().and_then(|d| d.get("scope")) {
+        Some(serde_json::Value::Array(values)) => values
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_json::Value::String(s)) => s.split_whitespace().map(str::to_string).collect(),
+        _ => vec![],
+    }
+}
+
+/// How one credential's scopes compare to what its login asks for.
+#[derive(Debug, Clone)]
+pub struct ScopeReport {
+    pub credential_id: Uuid,
+    pub platform: Platform,
+    pub user_name: String,
+    pub is_broadcaster: bool,
+    pub missing: Vec<String>,
+    /// Features that need one of the missing scopes
+    pub affected: Vec<&'static str>,
+    /// True when Twitch reported the scopes; false when only the stored list was checked
+    pub verified: bool,
+    /// Why Twitch couldn't be asked, if it wasn't
+    pub error: Option<String>,
+}
+
+impl ScopeReport {
+    /// Compares `granted` to what `cred`'s platform requires.
+    pub fn new(cred: &PlatformCredential, granted: &[String], verified: bool, error: Option<String>) -> Self {
+        let missing: Vec<String> = required_scopes(&cred.platform).iter()
+            .filter(|s| !granted.iter().any(|g| g == *s))
+            .map(|s| s.to_string())
+            .collect();
+        let affected = TWITCH_FEATURES.iter()
+            .filter(|f| f.applies_to(&cred.platform) && f.scopes.iter().any(|s| missing.iter().any(|m| m == s)))
+            .map(|f| f.name)
+            .collect();
+        Self {
+            credential_id: cred.credential_id,
+            platform: cred.platform.clone(),
+            user_name: cred.user_name.clone(),
+            is_broadcaster: cred.is_broadcaster,
+            missing,
+            affected,
+            verified,
+            error,
+        }
+    }
+
+    /// The TUI command that logs this account in again with the current scopes.
+    pub fn reauth_command(&self) -> Option<String> {
+        if self.missing.is_empty() {
+            return None;
+        }
+        Some(format!("account reauth {} {}", self.platform, self.user_name))
+    }
+
+    /// Whether the features this credential misses scopes for actually run on it:
+    /// Helix and EventSub features use the broadcaster's login, chat uses every IRC login.
+    fn degrades_features(&self) -> bool {
+        self.is_broadcaster || self.platform == Platform::TwitchIRC
+    }
+}
+
+/// A feature that won't fully work until someone logs in again.
+#[derive(Debug, Clone)]
+pub struct DegradedFeature {
+    pub feature: &'static ScopedFeature,
+    pub missing: Vec<String>,
+    /// Commands that fix it, one per affected login
+    pub reauth_commands: Vec<String>,
+}
+
+pub struct ScopeCheckService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    last: Mutex<Option<(Vec<ScopeReport>, DateTime<Utc>)>>,
+}
+
+impl ScopeCheckService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self { credentials_repo, event_bus, last: Mutex::new(None) }
+    }
+
+    /// Runs the startup check in the background.
+    pub fn start(self: &Arc<Self>) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = this.check_all().await {
+                warn!("Twitch scope check failed: {:?}", e);
+            }
+        });
+    }
+
+    /// Checks every Twitch credential, asking Twitch which scopes each token
+    /// has and falling back to the stored list when it can't be reached.
+    pub async fn check_all(&self) -> Result<Vec<ScopeReport>, Error> {
+        let mut reports = Vec::new();
+        for platform in [Platform::Twitch, Platform::TwitchIRC, Platform::TwitchEventSub] {
+            for cred in self.credentials_repo.list_credentials_for_platform(&platform).await? {
+                reports.push(Self::check_credential(&cred).await);
+            }
+        }
+
+        let previous = self.last.lock().take().map(|(reports, _)| reports).unwrap_or_default();
+        for report in reports.iter().filter(|r| !r.missing.is_empty()) {
+            warn!(
+                "Twitch {} login '{}' is missing scope(s) {}; degraded: {}",
+                report.platform, report.user_name, report.missing.join(", "), report.affected.join(", ")
+            );
+            // Say it once per change, not on every recheck
+            let unchanged = previous.iter()
+                .any(|p| p.credential_id == report.credential_id && p.missing == report.missing);
+            if !unchanged {
+                if let Some(command) = report.reauth_command() {
+                    self.event_bus.publish(BotEvent::SystemMessage(format!(
+                        "The {} login for {} needs new permissions ({}). Run `{}` to grant them.",
+                        report.platform, report.user_name, report.missing.join(", "), command
+                    ))).await;
+                }
+            }
+        }
+        if reports.iter().all(|r| r.missing.is_empty()) {
+            info!("All {} Twitch credential(s) have the scopes they need", reports.len());
+        }
+
+        *self.last.lock() = Some((reports.clone(), Utc::now()));
+        Ok(reports)
+    }
+
+    async fn check_credential(cred: &PlatformCredential) -> ScopeReport {
+        let token = cred.primary_token.trim_start_matches("oauth:");
+        let client = TwitchHelixClient::new(token, "");
+        let error = match tokio::time::timeout(VALIDATE_TIMEOUT, client.validate_token()).await {
+            Ok(Ok(Some(valid))) => return ScopeReport::new(cred, &valid.scopes, true, None),
+            Ok(Ok(None)) => "Twitch rejected the token".to_string(),
+            Ok(Err(e)) => format!("Could not reach Twitch: {}", e),
+            Err(_) => "Twitch did not answer in time".to_string(),
+        };
+        ScopeReport::new(cred, &stored_scopes(cred), false, Some(error))
+    }
+
+    /// The last check's reports and when it ran; `None` before the first check finishes.
+    pub fn last_reports(&self) -> Option<(Vec<ScopeReport>, DateTime<Utc>)> {
+        self.last.lock().clone()
+    }
+
+    /// Features missing a scope on the login they run on.
+    pub fn degraded_features(&self) -> Vec<DegradedFeature> {
+        let Some((reports, _)) = self.last_reports() else { return Vec::new() };
+        TWITCH_FEATURES.iter()
+            .filter_map(|feature| {
+                let reports: Vec<&ScopeReport> = reports.iter()
+                    .filter(|r| r.degrades_features() && r.affected.contains(&feature.name))
+                    .collect();
+                if reports.is_empty() {
+                    return None;
+                }
+                let missing: Vec<String> = feature.scopes.iter()
+                    .filter(|s| reports.iter().any(|r| r.missing.iter().any(|m| m == *s)))
+                    .map(|s| s.to_string())
+                    .collect();
+                Some(DegradedFeature {
+                    feature,
+                    missing,
+                    reauth_commands: reports.iter().filter_map(|r| r.reauth_command()).collect(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn is_degraded(&self, feature: &str) -> bool {
+        self.degraded_features().iter().any(|d| d.feature.name == feature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_feature_scope_is_requested_at_login() {
+        for feature in TWITCH_FEATURES {
+            for scope in feature.scopes {
+                assert!(
+                    required_scopes(&feature.platform).contains(scope),
+                    "{} needs {} but logins for {} don't ask for it", feature.name, scope, feature.platform
+                );
+            }
+        }
+    }
+}
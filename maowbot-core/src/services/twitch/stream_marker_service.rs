@@ -0,0 +1,190 @@
+// File: maowbot-core/src/services/twitch/stream_marker_service.rs
+//
+// Places Helix stream markers on notable moments (raids, big redeems, OBS
+// scene changes, `!marker`) and, when the stream ends, turns the broadcast's
+// markers into a chapter list stored against its VOD for editors.
+
+use std::sync::Arc;
+use chrono::Utc;
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::stream_marker::{chapter_list, MarkerSource, StreamMarker, VodChapters};
+use maowbot_common::traits::repository_traits::{CredentialsRepository, StreamMarkerRepository};
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData};
+use crate::services::twitch::{broadcaster_helix, BroadcasterHelix};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// The broadcast markers are currently being placed in.
+#[derive(Debug, Clone)]
+struct LiveStream {
+    stream_id: String,
+    title: String,
+}
+
+pub struct StreamMarkerService {
+    repo: Arc<dyn StreamMarkerRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    settings: Arc<SettingsRegistry>,
+    event_bus: Arc<EventBus>,
+    live: Mutex<Option<LiveStream>>,
+}
+
+impl StreamMarkerService {
+    pub fn new(
+        repo: Arc<dyn StreamMarkerRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        settings: Arc<SettingsRegistry>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self {
+            repo,
+            credentials_repo,
+            settings,
+            event_bus,
+            live: Mutex::new(None),
+        }
+    }
+
+    /// Listens for the events that place markers, and for stream.offline to
+    /// export the chapter list.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Markers] event loop stopped");
+        });
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        if !self.settings.get_bool("markers.enabled").unwrap_or(true) {
+            return;
+        }
+        let (source, description) = match event {
+            BotEvent::TwitchEventSub(TwitchEventSubData::StreamOnline(evt)) => {
+                *self.live.lock() = Some(LiveStream { stream_id: evt.id, title: String::new() });
+                return;
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::StreamOffline(_)) => {
+                let Some(stream) = self.live.lock().take() else { return };
+                if let Err(e) = self.export_chapters(&stream.stream_id, &stream.title).await {
+                    warn!("[Markers] could not export chapters for stream {}: {:?}", stream.stream_id, e);
+                }
+                return;
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelRaid(evt)) => {
+                if !self.settings.get_bool("markers.on_raid").unwrap_or(true) {
+                    return;
+                }
+                (MarkerSource::Raid, format!("Raid from {} ({} viewers)", evt.from_broadcaster_user_name, evt.viewers))
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(evt)) => {
+                let min_cost = self.settings.get_u64("markers.redeem_min_cost").unwrap_or(0);
+                if min_cost == 0 || evt.reward.cost < min_cost {
+                    return;
+                }
+                (MarkerSource::Redeem, format!("{} redeemed {}", evt.user_name, evt.reward.title))
+            }
+            BotEvent::ObsSceneChanged { scene, .. } => {
+                if !self.settings.get_bool("markers.on_scene_change").unwrap_or(true) {
+                    return;
+                }
+                (MarkerSource::Scene, format!("Scene: {}", scene))
+            }
+            _ => return,
+        };
+
+        if let Err(e) = self.create_marker(source, &description).await {
+            debug!("[Markers] no marker for {} ({}): {:?}", source, description, e);
+        }
+    }
+
+    /// Places a marker now and records it. Fails when the channel is offline.
+    pub async fn create_marker(&self, source: MarkerSource, description: &str) -> Result<StreamMarker, Error> {
+        let BroadcasterHelix { broadcaster_id, client, .. } = broadcaster_helix(&*self.credentials_repo).await?;
+
+        // Went live before the bot started: learn the broadcast from Helix
+        let known = self.live.lock().clone();
+        let stream = match known {
+            Some(stream) => stream,
+            None => {
+                let live = client.fetch_live_stream(&broadcaster_id).await?
+                    .ok_or_else(|| Error::Platform("The channel is not live".into()))?;
+                let stream = LiveStream { stream_id: live.id, title: live.title };
+                *self.live.lock() = Some(stream.clone());
+                stream
+            }
+        };
+
+        let created = client.create_stream_marker(&broadcaster_id, description).await?;
+        let marker = StreamMarker {
+            marker_id: Uuid::new_v4(),
+            broadcaster_id,
+            stream_id: stream.stream_id,
+            twitch_marker_id: Some(created.id),
+            position_seconds: created.position_seconds,
+            description: description.to_string(),
+            source,
+            created_at: created.created_at,
+        };
+        self.repo.insert_marker(&marker).await?;
+        info!("[Markers] {} marker at {}s: {}", source, marker.position_seconds, description);
+        Ok(marker)
+    }
+
+    /// Builds and stores the chapter list for a finished broadcast, linking it
+    /// to the archived VOD when Twitch has one.
+    pub async fn export_chapters(&self, stream_id: &str, title: &str) -> Result<VodChapters, Error> {
+        let markers = self.repo.list_markers(stream_id).await?;
+        let BroadcasterHelix { broadcaster_id, client, .. } = broadcaster_helix(&*self.credentials_repo).await?;
+        let video = match client.fetch_archive_video(&broadcaster_id, stream_id).await {
+            Ok(video) => video,
+            Err(e) => {
+                warn!("[Markers] could not look up the VOD for stream {}: {:?}", stream_id, e);
+                None
+            }
+        };
+
+        let title = video.as_ref().map(|v| v.title.clone())
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| title.to_string());
+        let chapters = VodChapters {
+            stream_id: stream_id.to_string(),
+            broadcaster_id,
+            video_id: video.as_ref().map(|v| v.id.clone()),
+            video_url: video.map(|v| v.url),
+            title,
+            chapters: chapter_list(&markers, "Start"),
+            marker_count: markers.len() as i32,
+            exported_at: Utc::now(),
+        };
+        self.repo.save_vod_chapters(&chapters).await?;
+        info!("[Markers] exported {} chapter marker(s) for stream {}", markers.len(), stream_id);
+        Ok(chapters)
+    }
+
+    pub async fn get_chapters(&self, id: &str) -> Result<Option<VodChapters>, Error> {
+        self.repo.get_vod_chapters(id).await
+    }
+
+    pub async fn list_chapters(&self, limit: i64) -> Result<Vec<VodChapters>, Error> {
+        self.repo.list_vod_chapters(limit).await
+    }
+}
@@ -0,0 +1,395 @@
+// File: maowbot-core/src/services/twitch/stream_session_service.rs
+//
+// Keeps one `stream_sessions` row per broadcast: opened at stream.online,
+// closed at stream.offline, with running totals of chat, follows, subs, gifts,
+// bits, raids, redemptions and hype moments, and the peak viewer count. The
+// totals and per-chatter message counts are kept in memory and written every
+// minute, so a crash loses at most a minute of counts. At startup a live
+// broadcast gets (or keeps) its session and sessions left open by a previous
+// run are closed at their last update. Analytics, recaps and leaderboards
+// resolve a `SessionScope` here to tell "this stream" from "all time".
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::stream_marker::StreamMarker;
+use maowbot_common::models::stream_session::{SessionChatter, SessionScope, SessionStats, StreamSession};
+use maowbot_common::traits::repository_traits::{CredentialsRepository, StreamMarkerRepository, StreamSessionRepository};
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData};
+use crate::services::message_dedupe::MessageDedupe;
+use crate::services::twitch::broadcaster_helix;
+use crate::Error;
+
+/// How often counts are written and the viewer count is polled.
+const FLUSH_INTERVAL_SECS: u64 = 60;
+
+/// The live session and what was counted since the last flush.
+struct LiveSession {
+    session: StreamSession,
+    /// Messages per chatter since the last flush: id -> (display name, count)
+    chatters: HashMap<String, (String, i64)>,
+    /// Drops the copy of a chat line seen by a second joined account
+    seen: MessageDedupe,
+}
+
+impl LiveSession {
+    fn new(session: StreamSession) -> Self {
+        Self {
+            session,
+            chatters: HashMap::new(),
+            seen: MessageDedupe::default(),
+        }
+    }
+}
+
+fn not_found(scope: SessionScope) -> Error {
+    match scope {
+        SessionScope::Current => Error::NotFound("The channel is not live".to_string()),
+        _ => Error::NotFound(format!("No stream session '{}'", scope)),
+    }
+}
+
+pub struct StreamSessionService {
+    repo: Arc<dyn StreamSessionRepository>,
+    marker_repo: Arc<dyn StreamMarkerRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    live: Mutex<Option<LiveSession>>,
+}
+
+impl StreamSessionService {
+    pub fn new(
+        repo: Arc<dyn StreamSessionRepository>,
+        marker_repo: Arc<dyn StreamMarkerRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self {
+            repo,
+            marker_repo,
+            credentials_repo,
+            event_bus,
+            live: Mutex::new(None),
+        }
+    }
+
+    /// Picks up a broadcast already in progress, then follows stream.online
+    /// and stream.offline and counts what happens in between.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.resume().await {
+                warn!("[Sessions] could not check for a live broadcast: {:?}", e);
+            }
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            let mut flush = tokio::time::interval(StdDuration::from_secs(FLUSH_INTERVAL_SECS));
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(event).await,
+                        None => break,
+                    },
+                    _ = flush.tick() => service.flush(true).await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            service.flush(false).await;
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[Sessions] event loop stopped");
+        });
+    }
+
+    async fn handle_event(&self, event: BotEvent) {
+        match event {
+            BotEvent::TwitchEventSub(TwitchEventSubData::StreamOnline(evt)) => {
+                let channel = evt.broadcaster_user_login.to_lowercase();
+                if let Err(e) = self.open(&channel, &evt.broadcaster_user_id, &evt.id, evt.started_at).await {
+                    warn!("[Sessions] could not open a session for stream {}: {:?}", evt.id, e);
+                }
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::StreamOffline(_)) => {
+                if let Err(e) = self.close(Utc::now()).await {
+                    warn!("[Sessions] could not close the session: {:?}", e);
+                }
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelUpdate(evt)) => {
+                let session_id = {
+                    let mut live = self.live.lock();
+                    let Some(live) = live.as_mut() else { return };
+                    live.session.title = evt.title.clone();
+                    live.session.category = evt.category_name.clone();
+                    live.session.session_id
+                };
+                if let Err(e) = self.repo.update_details(session_id, &evt.title, &evt.category_name).await {
+                    warn!("[Sessions] could not update the title of {}: {:?}", session_id, e);
+                }
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelFollow(_)) => self.count(|s| s.follows += 1),
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelSubscribe(evt)) if !evt.is_gift => self.count(|s| s.subs += 1),
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelSubscriptionMessage(_)) => self.count(|s| s.subs += 1),
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelSubscriptionGift(evt)) => {
+                self.count(|s| s.gifted_subs += evt.total as i64)
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelCheer(evt)) => self.count(|s| s.bits += evt.bits as i64),
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelRaid(evt)) => {
+                let incoming = self.live.lock().as_ref()
+                    .is_some_and(|live| live.session.broadcaster_id == evt.to_broadcaster_user_id);
+                if incoming {
+                    self.count(|s| s.raids += 1);
+                }
+            }
+            BotEvent::TwitchEventSub(TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(_)) => {
+                self.count(|s| s.redemptions += 1)
+            }
+            BotEvent::HypeMoment { .. } => self.count(|s| s.hype_moments += 1),
+            BotEvent::ChatMessage { platform, channel, metadata, .. } if platform == "twitch-irc" => {
+                let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                let channel = channel.trim_start_matches('#').to_lowercase();
+                let mut live = self.live.lock();
+                let Some(live) = live.as_mut() else { return };
+                if live.session.channel != channel {
+                    return;
+                }
+                let message_id = field("message_id");
+                if !message_id.is_empty() && !live.seen.first_sighting(&message_id) {
+                    return;
+                }
+                live.session.stats.chat_messages += 1;
+                let user_id = field("platform_user_id");
+                if !user_id.is_empty() {
+                    let entry = live.chatters.entry(user_id).or_insert_with(|| (String::new(), 0));
+                    entry.0 = field("username");
+                    entry.1 += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies `f` to the live session's totals; nothing while offline.
+    fn count(&self, f: impl FnOnce(&mut SessionStats)) {
+        if let Some(live) = self.live.lock().as_mut() {
+            f(&mut live.session.stats);
+        }
+    }
+
+    /// At startup: keeps counting into the session of a broadcast in progress,
+    /// and closes sessions a previous run left open.
+    async fn resume(&self) -> Result<(), Error> {
+        let helix = broadcaster_helix(&*self.credentials_repo).await?;
+        match helix.client.fetch_live_stream(&helix.broadcaster_id).await? {
+            Some(stream) => {
+                let started_at = DateTime::parse_from_rfc3339(&stream.started_at)
+                    .map(|t| t.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now());
+                self.open(&helix.login, &helix.broadcaster_id, &stream.id, started_at).await
+            }
+            None => self.close_stale(&helix.login, None).await,
+        }
+    }
+
+    /// Starts counting into the session of `stream_id`, creating it unless a
+    /// previous run already did. Any other open session of the channel is closed.
+    async fn open(&self, channel: &str, broadcaster_id: &str, stream_id: &str, started_at: DateTime<Utc>) -> Result<(), Error> {
+        if self.live.lock().as_ref().is_some_and(|live| live.session.stream_id == stream_id) {
+            return Ok(());
+        }
+        self.flush(false).await;
+        *self.live.lock() = None;
+        self.close_stale(channel, Some(stream_id)).await?;
+
+        let session = match self.repo.get_session_by_stream_id(stream_id).await? {
+            Some(session) => {
+                info!("[Sessions] resuming session {} of stream {}", session.session_id, stream_id);
+                session
+            }
+            None => {
+                let (title, category) = self.stream_details(broadcaster_id).await;
+                let session = StreamSession {
+                    session_id: Uuid::new_v4(),
+                    platform: "twitch".to_string(),
+                    channel: channel.to_string(),
+                    broadcaster_id: broadcaster_id.to_string(),
+                    stream_id: stream_id.to_string(),
+                    title,
+                    category,
+                    started_at,
+                    ended_at: None,
+                    stats: SessionStats::default(),
+                    updated_at: Utc::now(),
+                };
+                self.repo.create_session(&session).await?;
+                info!("[Sessions] opened session {} for stream {} on #{}", session.session_id, stream_id, channel);
+                session
+            }
+        };
+        *self.live.lock() = Some(LiveSession::new(session));
+        Ok(())
+    }
+
+    /// Title and category of the live broadcast; empty if Helix doesn't know yet.
+    async fn stream_details(&self, broadcaster_id: &str) -> (String, String) {
+        let live = match broadcaster_helix(&*self.credentials_repo).await {
+            Ok(helix) => helix.client.fetch_live_stream(broadcaster_id).await,
+            Err(e) => Err(e),
+        };
+        match live {
+            Ok(Some(stream)) => (stream.title, stream.game_name),
+            Ok(None) => (String::new(), String::new()),
+            Err(e) => {
+                debug!("[Sessions] no stream details for {}: {:?}", broadcaster_id, e);
+                (String::new(), String::new())
+            }
+        }
+    }
+
+    /// Closes open sessions of `channel` other than `keep_stream_id` at their
+    /// last update, as the bot wasn't there to see them end.
+    async fn close_stale(&self, channel: &str, keep_stream_id: Option<&str>) -> Result<(), Error> {
+        while let Some(stale) = self.repo.get_open_session(channel).await? {
+            if Some(stale.stream_id.as_str()) == keep_stream_id {
+                break;
+            }
+            info!("[Sessions] closing session {} left open since {}", stale.session_id, stale.updated_at);
+            self.repo.close_session(stale.session_id, stale.updated_at.max(stale.started_at)).await?;
+        }
+        Ok(())
+    }
+
+    async fn close(&self, ended_at: DateTime<Utc>) -> Result<(), Error> {
+        self.flush(false).await;
+        let Some(live) = self.live.lock().take() else { return Ok(()) };
+        self.repo.close_session(live.session.session_id, ended_at).await?;
+        info!(
+            "[Sessions] closed session {}: {} messages from {} chatters",
+            live.session.session_id, live.session.stats.chat_messages, live.session.stats.unique_chatters
+        );
+        Ok(())
+    }
+
+    /// Writes the counts gathered so far, after polling the viewer count when
+    /// `poll_viewers` is set.
+    async fn flush(&self, poll_viewers: bool) {
+        let Some(broadcaster_id) = self.live.lock().as_ref().map(|l| l.session.broadcaster_id.clone()) else { return };
+
+        let viewers = if poll_viewers {
+            match broadcaster_helix(&*self.credentials_repo).await {
+                Ok(helix) => helix.client.fetch_live_stream(&broadcaster_id).await.ok().flatten()
+                    .map(|stream| stream.viewer_count as i64),
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+
+        let (session_id, chatters) = {
+            let mut live = self.live.lock();
+            let Some(live) = live.as_mut() else { return };
+            if let Some(viewers) = viewers {
+                live.session.stats.peak_viewers = live.session.stats.peak_viewers.max(viewers);
+            }
+            let chatters: Vec<(String, String, i64)> = live.chatters.drain()
+                .map(|(id, (name, messages))| (id, name, messages))
+                .collect();
+            (live.session.session_id, chatters)
+        };
+
+        if !chatters.is_empty() {
+            if let Err(e) = self.repo.add_chatter_messages(session_id, &chatters).await {
+                warn!("[Sessions] could not store chatters of {}: {:?}", session_id, e);
+                // Put them back for the next flush
+                if let Some(live) = self.live.lock().as_mut().filter(|l| l.session.session_id == session_id) {
+                    for (id, name, messages) in chatters {
+                        let entry = live.chatters.entry(id).or_insert_with(|| (name, 0));
+                        entry.1 += messages;
+                    }
+                }
+            }
+        }
+        let unique = self.repo.count_chatters(session_id).await.ok();
+
+        let stats = {
+            let mut live = self.live.lock();
+            let Some(live) = live.as_mut().filter(|l| l.session.session_id == session_id) else { return };
+            if let Some(unique) = unique {
+                live.session.stats.unique_chatters = unique;
+            }
+            live.session.stats.clone()
+        };
+        if let Err(e) = self.repo.save_stats(session_id, &stats).await {
+            warn!("[Sessions] could not save stats of {}: {:?}", session_id, e);
+        }
+    }
+
+    /// The live session with its latest counts, if the channel is live.
+    pub fn current(&self) -> Option<StreamSession> {
+        self.live.lock().as_ref().map(|live| live.session.clone())
+    }
+
+    /// The session a scope refers to; `AllTime` refers to none.
+    pub async fn resolve(&self, scope: SessionScope) -> Result<Option<StreamSession>, Error> {
+        match scope {
+            SessionScope::Current => Ok(self.current()),
+            SessionScope::Latest => match self.current() {
+                Some(session) => Ok(Some(session)),
+                None => self.repo.get_latest_session().await,
+            },
+            SessionScope::Id(id) => match self.current().filter(|s| s.session_id == id) {
+                Some(session) => Ok(Some(session)),
+                None => self.repo.get_session(id).await,
+            },
+            SessionScope::AllTime => Ok(None),
+        }
+    }
+
+    /// The time window a scope covers; None for all time. Fails when the scope
+    /// names a session that doesn't exist, e.g. `current` while offline.
+    pub async fn window(&self, scope: SessionScope) -> Result<Option<(DateTime<Utc>, DateTime<Utc>)>, Error> {
+        if scope == SessionScope::AllTime {
+            return Ok(None);
+        }
+        self.resolve(scope).await?
+            .map(|session| Some(session.window()))
+            .ok_or_else(|| not_found(scope))
+    }
+
+    /// Newest first; the live session shows its latest counts.
+    pub async fn list(&self, limit: i64) -> Result<Vec<StreamSession>, Error> {
+        let mut sessions = self.repo.list_sessions(limit).await?;
+        if let Some(current) = self.current() {
+            for session in sessions.iter_mut().filter(|s| s.session_id == current.session_id) {
+                *session = current.clone();
+            }
+        }
+        Ok(sessions)
+    }
+
+    /// A session with its markers and busiest chatters.
+    pub async fn details(
+        &self,
+        scope: SessionScope,
+        top_chatters: i64,
+    ) -> Result<(StreamSession, Vec<StreamMarker>, Vec<SessionChatter>), Error> {
+        if scope == SessionScope::AllTime {
+            return Err(Error::ValidationError("Pick a session: current, last or a session id".into()));
+        }
+        // Write pending chatters so the live leaderboard is current
+        if self.current().is_some() {
+            self.flush(false).await;
+        }
+        let session = self.resolve(scope).await?.ok_or_else(|| not_found(scope))?;
+        let markers = self.marker_repo.list_markers(&session.stream_id).await?;
+        let chatters = self.repo.top_chatters(session.session_id, top_chatters).await?;
+        Ok((session, markers, chatters))
+    }
+}
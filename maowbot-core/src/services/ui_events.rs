@@ -0,0 +1,354 @@
+// File: maowbot-core/src/services/ui_events.rs
+//
+// The feed behind the `SubscribeEvents` stream that the GUI, TUI and overlay
+// follow: chat, alerts, connection changes and periodic OSC summaries. Every
+// event gets a sequence number and is written to the event journal
+// (bot_events), so a client that reconnects with the last number it saw is
+// sent what it missed before the live feed resumes. The most recent events
+// are also kept in memory, which covers short reconnects without a query.
+// While `alerts.muted` is on, alerts still go out but carry `muted: true`, so
+// clients can list them without popping them up or playing a sound.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde_json::{Map, Value};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, warn};
+
+use maowbot_osc::MaowOscManager;
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData};
+use crate::services::event_pipeline::variables::event_variables;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// Events kept in memory for resuming without touching the journal.
+const RECENT_CAPACITY: usize = 1000;
+/// Most events replayed to one resuming client; older ones are reported as a gap.
+const MAX_BACKFILL: i64 = 5000;
+/// Live events buffered per client; a client further behind misses events.
+const LIVE_BUFFER: usize = 1024;
+/// Events waiting to be journaled; new ones aren't journaled past this.
+const JOURNAL_QUEUE: usize = 4096;
+const JOURNAL_BATCH: usize = 200;
+const OSC_SUMMARY_SECS: u64 = 15;
+const SEED_RETRY_SECS: u64 = 5;
+
+/// What a UI event is about; clients subscribe to the kinds they show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiEventKind {
+    Chat,
+    Alert,
+    Connection,
+    Osc,
+}
+
+impl UiEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UiEventKind::Chat => "chat",
+            UiEventKind::Alert => "alert",
+            UiEventKind::Connection => "connection",
+            UiEventKind::Osc => "osc",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<UiEventKind> {
+        match s {
+            "chat" => Some(UiEventKind::Chat),
+            "alert" => Some(UiEventKind::Alert),
+            "connection" => Some(UiEventKind::Connection),
+            "osc" => Some(UiEventKind::Osc),
+            _ => None,
+        }
+    }
+
+    /// The kind a bus event is shown as, or `None` if UIs don't get it.
+    pub fn of(event: &BotEvent) -> Option<UiEventKind> {
+        match event {
+            BotEvent::ChatMessage { .. } => Some(UiEventKind::Chat),
+            BotEvent::Donation(_) | BotEvent::Kick(_) | BotEvent::ViewerMilestone(_) => Some(UiEventKind::Alert),
+            BotEvent::TwitchEventSub(
+                TwitchEventSubData::StreamOnline(_)
+                | TwitchEventSubData::StreamOffline(_)
+                | TwitchEventSubData::ChannelFollow(_)
+                | TwitchEventSubData::ChannelSubscribe(_)
+                | TwitchEventSubData::ChannelSubscriptionGift(_)
+                | TwitchEventSubData::ChannelSubscriptionMessage(_)
+                | TwitchEventSubData::ChannelCheer(_)
+                | TwitchEventSubData::ChannelBitsUse(_)
+                | TwitchEventSubData::ChannelRaid(_)
+                | TwitchEventSubData::ChannelHypeTrainBegin(_)
+                | TwitchEventSubData::ChannelHypeTrainEnd(_)
+                | TwitchEventSubData::ChannelPointsCustomRewardRedemptionAdd(_)
+            ) => Some(UiEventKind::Alert),
+            BotEvent::PlatformConnectionChanged { .. }
+            | BotEvent::CredentialRefreshFailed { .. }
+            | BotEvent::OpsAlert { .. } => Some(UiEventKind::Connection),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UiEvent {
+    /// Increases by one per event, across restarts
+    pub seq: i64,
+    pub kind: UiEventKind,
+    /// The bus event type (e.g. "channel.raid"), or "osc.summary"
+    pub event_type: String,
+    pub timestamp: DateTime<Utc>,
+    /// The event's fields, as pipeline variables name them (user, message, amount, ...)
+    pub data: Map<String, Value>,
+}
+
+/// Where a new subscriber starts: what it missed, then the live feed.
+pub struct UiSubscription {
+    /// Events after the resume point, oldest first
+    pub backfill: Vec<UiEvent>,
+    /// Some events after the resume point are gone (pruned, or too many to replay)
+    pub gap: bool,
+    pub live: broadcast::Receiver<UiEvent>,
+}
+
+/// The event journal (`bot_events`) that reconnecting subscribers replay from.
+#[async_trait]
+pub trait UiEventJournal: Send + Sync {
+    /// The highest journaled sequence number.
+    async fn last_seq(&self) -> Result<Option<i64>, Error>;
+    /// Stores a batch; events already journaled are skipped.
+    async fn insert_events(&self, batch: &[UiEvent]) -> Result<(), Error>;
+    /// Up to `limit` events with `after < seq < before`, newest first.
+    async fn events_between(&self, after: i64, before: i64, limit: i64) -> Result<Vec<UiEvent>, Error>;
+}
+
+struct Feed {
+    /// `None` until the last journaled number is known
+    next_seq: Option<i64>,
+    recent: VecDeque<UiEvent>,
+}
+
+pub struct UiEventStream {
+    journal: Arc<dyn UiEventJournal>,
+    event_bus: Arc<EventBus>,
+    osc_manager: Arc<MaowOscManager>,
+    settings: Arc<SettingsRegistry>,
+    live: broadcast::Sender<UiEvent>,
+    feed: Mutex<Feed>,
+    journal_tx: mpsc::Sender<UiEvent>,
+    journal_rx: Mutex<Option<mpsc::Receiver<UiEvent>>>,
+}
+
+impl UiEventStream {
+    pub fn new(
+        journal: Arc<dyn UiEventJournal>,
+        event_bus: Arc<EventBus>,
+        osc_manager: Arc<MaowOscManager>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        let (live, _) = broadcast::channel(LIVE_BUFFER);
+        let (journal_tx, journal_rx) = mpsc::channel(JOURNAL_QUEUE);
+        Self {
+            journal,
+            event_bus,
+            osc_manager,
+            settings,
+            live,
+            feed: Mutex::new(Feed { next_seq: None, recent: VecDeque::with_capacity(RECENT_CAPACITY) }),
+            journal_tx,
+            journal_rx: Mutex::new(Some(journal_rx)),
+        }
+    }
+
+    /// Starts the journal writer and, once the last sequence number is
+    /// loaded, forwards bus events and OSC summaries to subscribers.
+    pub fn start(self: &Arc<Self>) {
+        let Some(journal_rx) = self.journal_rx.lock().take() else { return };
+        tokio::spawn(write_journal(self.journal.clone(), journal_rx));
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            // Not subscribed to the bus yet, so a slow database can't hold up publishers
+            loop {
+                match service.seed().await {
+                    Ok(()) => break,
+                    Err(e) => warn!("[UiEvents] could not read the event journal, retrying: {:?}", e),
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(SEED_RETRY_SECS)) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut osc_interval = tokio::time::interval(Duration::from_secs(OSC_SUMMARY_SECS));
+            let mut last_osc: Option<(bool, u64, u64)> = None;
+            loop {
+                tokio::select! {
+                    maybe_event = rx.recv() => match maybe_event {
+                        Some(event) => service.handle_event(&event),
+                        None => break,
+                    },
+                    _ = osc_interval.tick() => service.summarize_osc(&mut last_osc).await,
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[UiEvents] event loop stopped");
+        });
+    }
+
+    async fn seed(&self) -> Result<(), Error> {
+        let last = self.journal.last_seq().await?;
+        self.feed.lock().next_seq = Some(last.unwrap_or(0) + 1);
+        Ok(())
+    }
+
+    fn handle_event(&self, event: &BotEvent) {
+        let Some(kind) = UiEventKind::of(event) else { return };
+        let mut data = event_variables(event);
+        if let BotEvent::ChatMessage { metadata, .. } = event {
+            // `user` is the bot's user id; UIs want the name too
+            for key in ["username", "display_name", "message_id"] {
+                if let Some(value) = metadata.get(key).filter(|v| v.is_string()) {
+                    data.insert(key.to_string(), value.clone());
+                }
+            }
+        }
+        if kind == UiEventKind::Alert && self.settings.get_bool("alerts.muted").unwrap_or(false) {
+            data.insert("muted".into(), true.into());
+        }
+        self.record(kind, event.event_type(), Utc::now(), data);
+    }
+
+    /// Publishes packet counts since the last summary, when there was traffic
+    /// or OSC started or stopped.
+    async fn summarize_osc(&self, last: &mut Option<(bool, u64, u64)>) {
+        let running = match self.osc_manager.get_status().await {
+            Ok(status) => status.is_running,
+            Err(_) => false,
+        };
+        let (received, sent) = self.osc_manager.counters.snapshot();
+        let Some((was_running, last_received, last_sent)) = last.replace((running, received, sent)) else {
+            return;
+        };
+        let (new_received, new_sent) = (received.saturating_sub(last_received), sent.saturating_sub(last_sent));
+        if running == was_running && new_received == 0 && new_sent == 0 {
+            return;
+        }
+        let mut data = Map::new();
+        data.insert("running".into(), running.into());
+        data.insert("received".into(), new_received.into());
+        data.insert("sent".into(), new_sent.into());
+        data.insert("interval_secs".into(), OSC_SUMMARY_SECS.into());
+        data.insert("total_received".into(), received.into());
+        data.insert("total_sent".into(), sent.into());
+        self.record(UiEventKind::Osc, "osc.summary".to_string(), Utc::now(), data);
+    }
+
+    fn record(&self, kind: UiEventKind, event_type: String, timestamp: DateTime<Utc>, data: Map<String, Value>) {
+        let event = {
+            let mut feed = self.feed.lock();
+            let Some(seq) = feed.next_seq else { return };
+            feed.next_seq = Some(seq + 1);
+            let event = UiEvent { seq, kind, event_type, timestamp, data };
+            if feed.recent.len() >= RECENT_CAPACITY {
+                feed.recent.pop_front();
+            }
+            feed.recent.push_back(event.clone());
+            // Sent under the lock so `subscribe` sees each event exactly once
+            let _ = self.live.send(event.clone());
+            event
+        };
+        if self.journal_tx.try_send(event).is_err() {
+            warn!("[UiEvents] journal queue is full; an event won't be replayable");
+        }
+    }
+
+    /// Subscribes to the live feed. With `after` (the last sequence number a
+    /// client saw), the events since then come first.
+    pub async fn subscribe(&self, after: Option<i64>) -> Result<UiSubscription, Error> {
+        let (mut backfill, live, first_recent, next_seq) = {
+            let feed = self.feed.lock();
+            let live = self.live.subscribe();
+            let backfill: Vec<UiEvent> = match after {
+                Some(after) => feed.recent.iter().filter(|e| e.seq > after).cloned().collect(),
+                None => Vec::new(),
+            };
+            let next_seq = feed.next_seq.unwrap_or(1);
+            let first_recent = feed.recent.front().map(|e| e.seq).unwrap_or(next_seq);
+            (backfill, live, first_recent, next_seq)
+        };
+
+        let Some(after) = after else {
+            return Ok(UiSubscription { backfill, gap: false, live });
+        };
+        if after >= next_seq {
+            // From before a journal reset, or not ours
+            return Ok(UiSubscription { backfill, gap: true, live });
+        }
+        let mut gap = false;
+        if after + 1 < first_recent {
+            let mut journaled = self.journal.events_between(after, first_recent, MAX_BACKFILL).await?;
+            let expected = first_recent - after - 1;
+            gap = (journaled.len() as i64) < expected;
+            journaled.reverse();
+            journaled.append(&mut backfill);
+            backfill = journaled;
+        }
+        Ok(UiSubscription { backfill, gap, live })
+    }
+}
+
+/// Writes queued events to bot_events in batches.
+async fn write_journal(journal: Arc<dyn UiEventJournal>, mut rx: mpsc::Receiver<UiEvent>) {
+    let mut batch = Vec::with_capacity(JOURNAL_BATCH);
+    while let Some(event) = rx.recv().await {
+        batch.push(event);
+        while batch.len() < JOURNAL_BATCH {
+            match rx.try_recv() {
+                Ok(event) => batch.push(event),
+                Err(_) => break,
+            }
+        }
+        if let Err(e) = journal.insert_events(&batch).await {
+            warn!("[UiEvents] could not journal {} event(s): {:?}", batch.len(), e);
+        }
+        batch.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_ui_events_get_a_kind() {
+        let chat = BotEvent::ChatMessage {
+            platform: "twitch-irc".into(),
+            channel: "#maow".into(),
+            user: "u".into(),
+            text: "hi".into(),
+            timestamp: Utc::now(),
+            metadata: Map::new(),
+        };
+        assert_eq!(UiEventKind::of(&chat), Some(UiEventKind::Chat));
+        assert_eq!(UiEventKind::of(&BotEvent::Tick), None);
+        assert_eq!(UiEventKind::of(&BotEvent::SystemMessage("x".into())), None);
+        for kind in [UiEventKind::Chat, UiEventKind::Alert, UiEventKind::Connection, UiEventKind::Osc] {
+            assert_eq!(UiEventKind::parse(kind.as_str()), Some(kind));
+        }
+    }
+}
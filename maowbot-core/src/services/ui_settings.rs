@@ -0,0 +1,154 @@
+// File: maowbot-core/src/services/ui_settings.rs
+//
+// Settings shared by the GUI and the VR overlay: layout ("ui"), audio
+// ("audio") and the stream overlay ("overlay"). Each section is a JSON
+// object kept in bot_config under `ui_settings.<section>`. An update only
+// replaces the fields it carries, so a client built before a field was added
+// doesn't wipe it, and every change is pushed to subscribers so the other
+// clients follow along live.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde_json::Value;
+use tokio::sync::{broadcast, Mutex as AsyncMutex};
+use tracing::{debug, warn};
+
+use maowbot_common::traits::repository_traits::BotConfigRepository;
+
+use crate::Error;
+
+pub const UI_SETTINGS_SECTIONS: [&str; 3] = ["ui", "audio", "overlay"];
+const CONFIG_PREFIX: &str = "ui_settings.";
+/// Changes queued per subscriber before the slowest ones miss some.
+const CHANGE_BUFFER: usize = 64;
+
+/// The stored settings of one section.
+#[derive(Debug, Clone)]
+pub struct UiSettingsSection {
+    pub section: String,
+    /// Always a JSON object
+    pub settings: Value,
+    /// Increases with every change since the server started
+    pub revision: u64,
+    /// The client that made the change; empty when loaded from the database
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Copies every top-level field of `update` into `stored`.
+pub fn merge_settings(stored: &mut Value, update: Value) {
+    match (stored.as_object_mut(), update) {
+        (Some(stored), Value::Object(update)) => {
+            for (key, value) in update {
+                stored.insert(key, value);
+            }
+        }
+        (_, update) => *stored = update,
+    }
+}
+
+pub struct UiSettingsStore {
+    bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>,
+    sections: RwLock<HashMap<String, UiSettingsSection>>,
+    revision: AtomicU64,
+    changes: broadcast::Sender<UiSettingsSection>,
+    /// Serializes updates so two clients saving at once don't drop each other's fields
+    update_lock: AsyncMutex<()>,
+}
+
+impl UiSettingsStore {
+    pub fn new(bot_config_repo: Arc<dyn BotConfigRepository + Send + Sync>) -> Self {
+        let (changes, _) = broadcast::channel(CHANGE_BUFFER);
+        Self {
+            bot_config_repo,
+            sections: RwLock::new(HashMap::new()),
+            revision: AtomicU64::new(0),
+            changes,
+            update_lock: AsyncMutex::new(()),
+        }
+    }
+
+    /// Reads the stored sections. Ones that fail to parse are left out, so
+    /// clients fall back to their defaults for them.
+    pub async fn load(&self) -> Result<(), Error> {
+        let mut loaded = HashMap::new();
+        for section in UI_SETTINGS_SECTIONS {
+            let Some(raw) = self.bot_config_repo.get_value(&format!("{CONFIG_PREFIX}{section}")).await? else {
+                continue;
+            };
+            match serde_json::from_str::<Value>(&raw) {
+                Ok(settings) if settings.is_object() => {
+                    loaded.insert(section.to_string(), UiSettingsSection {
+                        section: section.to_string(),
+                        settings,
+                        revision: self.revision.fetch_add(1, Ordering::Relaxed) + 1,
+                        updated_by: String::new(),
+                        updated_at: Utc::now(),
+                    });
+                }
+                _ => warn!("[UiSettings] ignoring unreadable '{}' settings", section),
+            }
+        }
+        debug!("[UiSettings] loaded {} section(s)", loaded.len());
+        *self.sections.write() = loaded;
+        Ok(())
+    }
+
+    /// The stored sections; sections no client has saved yet are missing.
+    pub fn get_all(&self) -> Vec<UiSettingsSection> {
+        let mut sections: Vec<_> = self.sections.read().values().cloned().collect();
+        sections.sort_by_key(|s| s.revision);
+        sections
+    }
+
+    /// Merges `update` into `section`, saves it and tells every subscriber.
+    pub async fn update(&self, section: &str, update: Value, updated_by: &str) -> Result<UiSettingsSection, Error> {
+        if !UI_SETTINGS_SECTIONS.contains(&section) {
+            return Err(Error::ValidationError(format!(
+                "Unknown settings section '{}' (expected {})", section, UI_SETTINGS_SECTIONS.join(", ")
+            )));
+        }
+        if !update.is_object() {
+            return Err(Error::ValidationError("Settings must be a JSON object".into()));
+        }
+
+        let _guard = self.update_lock.lock().await;
+        let mut settings = self.sections.read().get(section)
+            .map(|s| s.settings.clone())
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        merge_settings(&mut settings, update);
+        self.bot_config_repo.set_value(&format!("{CONFIG_PREFIX}{section}"), &settings.to_string()).await?;
+
+        let stored = UiSettingsSection {
+            section: section.to_string(),
+            settings,
+            revision: self.revision.fetch_add(1, Ordering::Relaxed) + 1,
+            updated_by: updated_by.to_string(),
+            updated_at: Utc::now(),
+        };
+        self.sections.write().insert(section.to_string(), stored.clone());
+        // No receivers just means no client is listening
+        let _ = self.changes.send(stored.clone());
+        Ok(stored)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<UiSettingsSection> {
+        self.changes.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_updates_keep_fields_they_do_not_carry() {
+        let mut stored = json!({ "show_chat": true, "chat_opacity": 0.8 });
+        merge_settings(&mut stored, json!({ "chat_opacity": 0.5, "alert_position": "Top Left" }));
+        assert_eq!(stored, json!({ "show_chat": true, "chat_opacity": 0.5, "alert_position": "Top Left" }));
+    }
+}
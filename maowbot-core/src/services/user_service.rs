@@ -1,17 +1,21 @@
 use std::sync::Arc;
+use chrono::Utc;
+use tracing::info;
 use maowbot_common::models::platform::{Platform, PlatformIdentity};
 use maowbot_common::models::user::User;
 use crate::Error;
 
 use crate::auth::user_manager::{UserManager, DefaultUserManager};
-use crate::repositories::postgres::user::UserRepo;
 use crate::repositories::postgres::platform_identity::PlatformIdentityRepo;
+use crate::services::identity_link::{LinkCodes, LinkOutcome};
 
 /// The UserService adds some higher-level operations on top of the raw user_manager,
 /// such as merging roles, etc.
 pub struct UserService {
     pub user_manager: Arc<DefaultUserManager>,
     pub platform_identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+    /// Codes handed out by `!link`, waiting to be sent from another platform
+    link_codes: LinkCodes,
 }
 
 impl UserService {
@@ -22,6 +26,7 @@ impl UserService {
         Self {
             user_manager,
             platform_identity_repo,
+            link_codes: LinkCodes::default(),
         }
     }
 
@@ -80,6 +85,7 @@ impl UserService {
             "vrchat" => Platform::VRChat,
             "twitch-irc" => Platform::TwitchIRC,
             "twitch-eventsub" => Platform::TwitchEventSub,
+            "kick" => Platform::Kick,
             other => return Err(Error::Platform(format!("Unknown platform: {}", other))),
         };
 
@@ -108,4 +114,44 @@ impl UserService {
             Err(Error::Platform(format!("No user with global_username='{}'", name)))
         }
     }
-}
\ No newline at end of file
+
+    /// Starts a self-service link: a code `user_id` sends from their account
+    /// on another platform to prove both are theirs.
+    pub fn start_identity_link(&self, user_id: uuid::Uuid, platform: Platform) -> String {
+        self.link_codes.issue(user_id, platform, Utc::now())
+    }
+
+    /// Redeems a `!link` code sent by `user_id` on `platform`, merging the
+    /// sender's user record with the one that asked for the code. The older
+    /// record is kept, with the newer one's identities and history moved onto it.
+    pub async fn link_identity_with_code(
+        &self,
+        code: &str,
+        user_id: uuid::Uuid,
+        platform: Platform,
+    ) -> Result<LinkOutcome, Error> {
+        let link = match self.link_codes.redeem(code, &platform, Utc::now()) {
+            Ok(link) => link,
+            Err(e) => return Ok(LinkOutcome::Failed(e)),
+        };
+        if link.user_id == user_id {
+            return Ok(LinkOutcome::AlreadyLinked);
+        }
+
+        let repo = &self.user_manager.user_repo;
+        let issuer = repo.get(link.user_id).await?
+            .ok_or_else(|| Error::NotFound(format!("User {} who asked for the link", link.user_id)))?;
+        let sender = repo.get(user_id).await?
+            .ok_or_else(|| Error::NotFound(format!("User {}", user_id)))?;
+        let (keep, merge) = if issuer.created_at <= sender.created_at { (issuer, sender) } else { (sender, issuer) };
+
+        repo.merge_users(keep.user_id, vec![merge.user_id]).await?;
+        self.user_manager.invalidate_user_id(keep.user_id);
+        self.user_manager.invalidate_user_id(merge.user_id);
+        info!(
+            "Linked user {} into user {} ({}) with a code sent from {}",
+            merge.user_id, keep.user_id, keep.global_username.as_deref().unwrap_or("unnamed"), platform
+        );
+        Ok(LinkOutcome::Linked(keep))
+    }
+}
@@ -0,0 +1,244 @@
+// File: maowbot-core/src/services/viewer_card.rs
+//
+// Everything the bot knows about one viewer, in one place: their identities and
+// roles on each platform, Twitch follow and sub status, channel point redeems,
+// moderator notes, recent chat and what the AI remembers about them. Each part
+// is looked up on its own; one that fails (Twitch unreachable, say) is left
+// empty and noted in `warnings` instead of failing the whole card.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use tracing::debug;
+use uuid::Uuid;
+
+use maowbot_common::models::analytics::ChatMessage;
+use maowbot_common::models::platform::{Platform, PlatformIdentity};
+use maowbot_common::models::user::User;
+use maowbot_common::models::user_analysis::UserAnalysis;
+use maowbot_common::models::user_notes::UserNote;
+use maowbot_common::traits::repository_traits::{
+    AiMemoryRepository, AnalyticsRepo, CredentialsRepository, PlatformIdentityRepo, RedeemRepository,
+    RedeemUsageRepository, UserAnalysisRepository, UserNotesRepository, UserRepo,
+};
+
+use crate::services::twitch::broadcaster_helix;
+use crate::Error;
+
+/// Redeem uses counted towards `points_spent` (the most recent ones).
+const MAX_REDEEMS_COUNTED: i64 = 1000;
+/// AI memories counted for the summary.
+const MAX_MEMORIES_COUNTED: i64 = 500;
+/// Memory snippets shown on the card, and how long each may be.
+const MEMORY_SNIPPETS: usize = 3;
+const MEMORY_SNIPPET_CHARS: usize = 120;
+const NOTES_SHOWN: i64 = 5;
+
+/// Follow and sub status on the broadcaster's Twitch channel.
+#[derive(Debug, Clone, Default)]
+pub struct TwitchStanding {
+    pub followed_at: Option<DateTime<Utc>>,
+    /// "1000", "2000" or "3000"; None when not subscribed
+    pub sub_tier: Option<String>,
+    pub sub_is_gift: bool,
+}
+
+/// What the AI remembers about the viewer.
+#[derive(Debug, Clone, Default)]
+pub struct AiMemorySummary {
+    pub memory_count: usize,
+    pub last_memory_at: Option<DateTime<Utc>>,
+    /// The latest things the viewer said to the AI, shortened
+    pub recent: Vec<String>,
+    /// Notes from the AI's user analysis
+    pub ai_notes: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewerCard {
+    pub user: User,
+    /// Roles are on each identity
+    pub identities: Vec<PlatformIdentity>,
+    pub twitch: Option<TwitchStanding>,
+    pub redeems_used: usize,
+    /// Channel points spent on those redeems, at each reward's current cost
+    pub points_spent: i64,
+    pub notes: Vec<UserNote>,
+    pub recent_messages: Vec<ChatMessage>,
+    pub analysis: Option<UserAnalysis>,
+    pub ai_memory: AiMemorySummary,
+    /// Parts of the card that couldn't be filled in, and why
+    pub warnings: Vec<String>,
+}
+
+/// One part of the card: its value, or None with the reason added to `warnings`.
+fn part<T>(warnings: &mut Vec<String>, what: &str, result: Result<T, Error>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            debug!("viewer card: no {}: {:?}", what, e);
+            warnings.push(format!("{what}: {e}"));
+            None
+        }
+    }
+}
+
+/// Shortens `text` to at most `max` characters, marking the cut.
+fn snippet(text: &str, max: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
+
+pub struct ViewerCardService {
+    user_repo: Arc<dyn UserRepo + Send + Sync>,
+    identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+    analysis_repo: Arc<dyn UserAnalysisRepository>,
+    notes_repo: Arc<dyn UserNotesRepository>,
+    analytics_repo: Arc<dyn AnalyticsRepo>,
+    redeem_repo: Arc<dyn RedeemRepository>,
+    redeem_usage_repo: Arc<dyn RedeemUsageRepository>,
+    memory_repo: Arc<dyn AiMemoryRepository>,
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+}
+
+impl ViewerCardService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repo: Arc<dyn UserRepo + Send + Sync>,
+        identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+        analysis_repo: Arc<dyn UserAnalysisRepository>,
+        notes_repo: Arc<dyn UserNotesRepository>,
+        analytics_repo: Arc<dyn AnalyticsRepo>,
+        redeem_repo: Arc<dyn RedeemRepository>,
+        redeem_usage_repo: Arc<dyn RedeemUsageRepository>,
+        memory_repo: Arc<dyn AiMemoryRepository>,
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            user_repo,
+            identity_repo,
+            analysis_repo,
+            notes_repo,
+            analytics_repo,
+            redeem_repo,
+            redeem_usage_repo,
+            memory_repo,
+            credentials_repo,
+        }
+    }
+
+    /// Finds a user by UUID or global username.
+    pub async fn resolve_user(&self, user: &str) -> Result<User, Error> {
+        let user = user.trim();
+        let found = match Uuid::parse_str(user) {
+            Ok(id) => self.user_repo.get(id).await?,
+            Err(_) => self.user_repo.get_by_global_username(user).await?,
+        };
+        found.ok_or_else(|| Error::NotFound(format!("No user '{user}'")))
+    }
+
+    /// The card for `user` (UUID or global username) with up to `message_limit`
+    /// recent chat messages.
+    pub async fn card(&self, user: &str, message_limit: i64) -> Result<ViewerCard, Error> {
+        let user = self.resolve_user(user).await?;
+        let user_id = user.user_id;
+        let identities = self.identity_repo.get_all_for_user(user_id).await?;
+        let mut warnings = Vec::new();
+
+        let twitch_id = identities.iter()
+            .find(|i| i.platform == Platform::Twitch || i.platform == Platform::TwitchIRC)
+            .map(|i| i.platform_user_id.clone());
+        let (twitch, points, notes, messages, analysis, memories) = tokio::join!(
+            async {
+                match &twitch_id {
+                    Some(id) => self.twitch_standing(id).await.map(Some),
+                    None => Ok(None),
+                }
+            },
+            self.points_spent(user_id),
+            self.notes_repo.list_notes(user_id, NOTES_SHOWN),
+            self.analytics_repo.get_messages_for_user(user_id, message_limit.max(0), 0, None, None, None),
+            self.analysis_repo.get_analysis(user_id),
+            self.memory_repo.list_memories_for_user(user_id, MAX_MEMORIES_COUNTED),
+        );
+
+        let twitch = part(&mut warnings, "twitch status", twitch).flatten();
+        let (redeems_used, points_spent) = part(&mut warnings, "redeems", points).unwrap_or_default();
+        let notes = part(&mut warnings, "notes", notes).unwrap_or_default();
+        let recent_messages = part(&mut warnings, "messages", messages).unwrap_or_default();
+        let analysis = part(&mut warnings, "analysis", analysis).flatten();
+        let memories = part(&mut warnings, "ai memory", memories).unwrap_or_default();
+
+        let ai_memory = AiMemorySummary {
+            memory_count: memories.len(),
+            last_memory_at: memories.first().map(|m| m.timestamp),
+            recent: memories.iter()
+                .filter(|m| m.role == "user")
+                .take(MEMORY_SNIPPETS)
+                .map(|m| snippet(&m.content, MEMORY_SNIPPET_CHARS))
+                .collect(),
+            ai_notes: analysis.as_ref().and_then(|a| a.ai_notes.clone()).filter(|n| !n.trim().is_empty()),
+        };
+
+        Ok(ViewerCard {
+            user,
+            identities,
+            twitch,
+            redeems_used,
+            points_spent,
+            notes,
+            recent_messages,
+            analysis,
+            ai_memory,
+            warnings,
+        })
+    }
+
+    async fn twitch_standing(&self, twitch_user_id: &str) -> Result<TwitchStanding, Error> {
+        let helix = broadcaster_helix(&*self.credentials_repo).await?;
+        let followed_at = helix.client.fetch_follow_date(twitch_user_id, &helix.broadcaster_id).await?;
+        let sub = helix.client.fetch_subscription(&helix.broadcaster_id, twitch_user_id).await?;
+        Ok(TwitchStanding {
+            followed_at,
+            sub_tier: sub.as_ref().map(|s| s.tier.clone()),
+            sub_is_gift: sub.is_some_and(|s| s.is_gift),
+        })
+    }
+
+    /// How many redeems the viewer used and the points they cost.
+    async fn points_spent(&self, user_id: Uuid) -> Result<(usize, i64), Error> {
+        let usages = self.redeem_usage_repo.list_usage_for_user(user_id, MAX_REDEEMS_COUNTED).await?;
+        let mut costs: HashMap<Uuid, i64> = HashMap::new();
+        let mut spent = 0;
+        for usage in &usages {
+            let cost = match costs.get(&usage.redeem_id) {
+                Some(cost) => *cost,
+                None => {
+                    let cost = self.redeem_repo.get_redeem_by_id(usage.redeem_id).await?
+                        .map(|r| i64::from(r.cost))
+                        .unwrap_or(0);
+                    costs.insert(usage.redeem_id, cost);
+                    cost
+                }
+            };
+            spent += cost;
+        }
+        Ok((usages.len(), spent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortens_long_snippets() {
+        assert_eq!(snippet("  hello  ", 10), "hello");
+        assert_eq!(snippet("one two three", 8), "one two…");
+        assert_eq!(snippet("ééééé", 3), "éé…");
+    }
+}
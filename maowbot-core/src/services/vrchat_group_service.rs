@@ -0,0 +1,300 @@
+// File: maowbot-core/src/services/vrchat_group_service.rs
+//
+// The streamer's VRChat group (`vrchat.group.id`). When the Twitch stream goes
+// live an announcement is posted to the group if `vrchat.group.announce_live`
+// is on. Join requests are polled every `vrchat.group.poll_minutes` so new ones
+// show up as `BotEvent::VRChatGroup` (pipelines can ping a mod), and they can
+// be approved or rejected from the TUI. VRChat calls go through the
+// `vrchat_active_account` login, which needs the group's manage-members and
+// post permissions.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::eventbus::{BotEvent, EventBus, TwitchEventSubData, VRChatGroupEventKind};
+use crate::platforms::vrchat::active_credential;
+use crate::platforms::vrchat::client::{is_session_expired, VRChatClient, VRChatGroupJoinRequest};
+use crate::services::twitch::broadcaster_helix;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// Join requests as of the last poll.
+#[derive(Debug, Clone, Default)]
+pub struct GroupRequestsSnapshot {
+    pub group_id: Option<String>,
+    pub group_name: Option<String>,
+    pub requests: Vec<VRChatGroupJoinRequest>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+/// Join requests in `current` that weren't pending in `previous`.
+pub fn new_requests<'a>(
+    previous: &[VRChatGroupJoinRequest],
+    current: &'a [VRChatGroupJoinRequest],
+) -> Vec<&'a VRChatGroupJoinRequest> {
+    current.iter()
+        .filter(|r| !previous.iter().any(|p| p.user_id == r.user_id))
+        .collect()
+}
+
+/// Fills `{user}`, `{title}`, `{category}` and `{link}` in an announcement template.
+pub fn fill_announcement(template: &str, values: &[(&str, &str)]) -> String {
+    let mut text = template.to_string();
+    for (key, value) in values {
+        text = text.replace(&format!("{{{key}}}"), value);
+    }
+    text.trim().to_string()
+}
+
+pub struct VRChatGroupService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    /// None until the first poll, which is the baseline rather than a wave of requests
+    snapshot: Mutex<Option<GroupRequestsSnapshot>>,
+}
+
+impl VRChatGroupService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            credentials_repo,
+            event_bus,
+            settings,
+            snapshot: Mutex::new(None),
+        }
+    }
+
+    /// Polls join requests every `vrchat.group.poll_minutes` and announces
+    /// going live, while a group is configured.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut rx = service.event_bus.subscribe(None).await;
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                if service.group_id().is_some() {
+                    if let Err(e) = service.poll().await {
+                        if is_session_expired(&e) {
+                            // The session service reports this; don't repeat it every poll
+                            debug!("[VRChatGroup] {}", e);
+                        } else {
+                            warn!("[VRChatGroup] poll failed: {:?}", e);
+                        }
+                    }
+                }
+                let minutes = service.settings.get_u64("vrchat.group.poll_minutes").unwrap_or(5).max(1);
+                let sleep = tokio::time::sleep(StdDuration::from_secs(minutes * 60));
+                tokio::pin!(sleep);
+                loop {
+                    tokio::select! {
+                        _ = &mut sleep => break,
+                        maybe_event = rx.recv() => match maybe_event {
+                            Some(BotEvent::TwitchEventSub(TwitchEventSubData::StreamOnline(_))) => {
+                                if let Err(e) = service.announce_live().await {
+                                    warn!("[VRChatGroup] live announcement failed: {:?}", e);
+                                }
+                            }
+                            Some(_) => {}
+                            None => return,
+                        },
+                        Ok(_) = shutdown_rx.changed() => {
+                            if *shutdown_rx.borrow() {
+                                debug!("[VRChatGroup] loop stopped");
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn group_id(&self) -> Option<String> {
+        self.settings.get("vrchat.group.id")
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// A client for the active account and the configured group.
+    async fn client(&self) -> Result<(VRChatClient, String), Error> {
+        let group_id = self.group_id()
+            .ok_or_else(|| Error::Platform("No VRChat group set; set vrchat.group.id to a grp_ id".into()))?;
+        let cred = active_credential(&*self.credentials_repo, &self.settings).await?
+            .ok_or_else(|| Error::NotFound("No VRChat credential for vrchat_active_account".into()))?;
+        Ok((VRChatClient::new(&cred.primary_token)?, group_id))
+    }
+
+    /// Join requests as of the last poll (empty before the first).
+    pub fn snapshot(&self) -> GroupRequestsSnapshot {
+        self.snapshot.lock().clone().unwrap_or_default()
+    }
+
+    /// Fetches pending join requests and publishes the new ones.
+    pub async fn poll(&self) -> Result<GroupRequestsSnapshot, Error> {
+        let (client, group_id) = self.client().await?;
+        let requests = client.fetch_group_join_requests(&group_id).await?;
+
+        let previous = self.snapshot.lock().clone()
+            .filter(|s| s.group_id.as_deref() == Some(group_id.as_str()));
+        let group_name = match previous.as_ref().and_then(|p| p.group_name.clone()) {
+            Some(name) => Some(name),
+            None => match client.fetch_group(&group_id).await {
+                Ok(group) => Some(group.name),
+                Err(e) => {
+                    debug!("[VRChatGroup] no group info for {}: {}", group_id, e);
+                    None
+                }
+            },
+        };
+
+        if let Some(previous) = &previous {
+            for request in new_requests(&previous.requests, &requests) {
+                info!("[VRChatGroup] {} asked to join", request.display_name);
+                self.publish(
+                    VRChatGroupEventKind::JoinRequest,
+                    &group_id,
+                    Some((&request.user_id, &request.display_name)),
+                    None,
+                ).await;
+            }
+        }
+
+        let snapshot = GroupRequestsSnapshot {
+            group_id: Some(group_id),
+            group_name,
+            requests,
+            checked_at: Some(Utc::now()),
+        };
+        *self.snapshot.lock() = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Approves or rejects the join request of `user`, a `usr_` id or the
+    /// display name of someone in the last poll.
+    pub async fn respond(&self, user: &str, accept: bool) -> Result<VRChatGroupJoinRequest, Error> {
+        let (client, group_id) = self.client().await?;
+        let known = self.snapshot().requests;
+        let request = known.iter()
+            .find(|r| r.user_id == user || r.display_name.eq_ignore_ascii_case(user))
+            .cloned()
+            .or_else(|| user.starts_with("usr_").then(|| VRChatGroupJoinRequest {
+                user_id: user.to_string(),
+                display_name: user.to_string(),
+                requested_at: None,
+            }))
+            .ok_or_else(|| Error::NotFound(format!("No pending join request from '{user}'")))?;
+
+        client.respond_group_join_request(&group_id, &request.user_id, accept).await?;
+        info!(
+            "[VRChatGroup] {} join request from {}",
+            if accept { "approved" } else { "rejected" },
+            request.display_name
+        );
+        if let Some(snapshot) = self.snapshot.lock().as_mut() {
+            snapshot.requests.retain(|r| r.user_id != request.user_id);
+        }
+        let kind = if accept { VRChatGroupEventKind::RequestApproved } else { VRChatGroupEventKind::RequestRejected };
+        self.publish(kind, &group_id, Some((&request.user_id, &request.display_name)), None).await;
+        Ok(request)
+    }
+
+    /// Posts an announcement to the group. Returns the post id.
+    pub async fn post(&self, title: &str, text: &str, notify: bool) -> Result<String, Error> {
+        if title.trim().is_empty() || text.trim().is_empty() {
+            return Err(Error::Parse("A group post needs a title and text".into()));
+        }
+        let (client, group_id) = self.client().await?;
+        let post_id = client.post_group_announcement(&group_id, title, text, notify).await?;
+        self.publish(VRChatGroupEventKind::Announcement, &group_id, None, Some(title)).await;
+        Ok(post_id)
+    }
+
+    /// The `vrchat.group.announce_*` post, when the stream goes live.
+    async fn announce_live(&self) -> Result<(), Error> {
+        if self.group_id().is_none() || !self.settings.get_bool("vrchat.group.announce_live").unwrap_or(false) {
+            return Ok(());
+        }
+        let helix = broadcaster_helix(&*self.credentials_repo).await?;
+        let channel = helix.client.get_channel_information(&helix.broadcaster_id).await?;
+        let (stream_title, category) = channel
+            .map(|c| (c.title, c.game_name))
+            .unwrap_or_default();
+        let link = format!("https://twitch.tv/{}", helix.login);
+        let values = [
+            ("user", helix.login.as_str()),
+            ("title", stream_title.as_str()),
+            ("category", category.as_str()),
+            ("link", link.as_str()),
+        ];
+        let title_template = self.settings.get("vrchat.group.announce_title")
+            .unwrap_or_else(|| "{user} is live!".to_string());
+        let text_template = self.settings.get("vrchat.group.announce_text")
+            .unwrap_or_else(|| "{title} ({category}) {link}".to_string());
+        let notify = self.settings.get_bool("vrchat.group.announce_notify").unwrap_or(true);
+
+        self.post(
+            &fill_announcement(&title_template, &values),
+            &fill_announcement(&text_template, &values),
+            notify,
+        ).await?;
+        info!("[VRChatGroup] posted the going-live announcement");
+        Ok(())
+    }
+
+    async fn publish(
+        &self,
+        kind: VRChatGroupEventKind,
+        group_id: &str,
+        user: Option<(&str, &str)>,
+        title: Option<&str>,
+    ) {
+        self.event_bus.publish(BotEvent::VRChatGroup {
+            kind,
+            group_id: group_id.to_string(),
+            user_id: user.map(|(id, _)| id.to_string()),
+            display_name: user.map(|(_, name)| name.to_string()),
+            title: title.map(String::from),
+            timestamp: Utc::now(),
+        }).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: &str, name: &str) -> VRChatGroupJoinRequest {
+        VRChatGroupJoinRequest {
+            user_id: id.to_string(),
+            display_name: name.to_string(),
+            requested_at: None,
+        }
+    }
+
+    #[test]
+    fn test_finds_only_new_join_requests() {
+        let previous = vec![request("usr_1", "Mochi")];
+        let current = vec![request("usr_1", "Mochi"), request("usr_2", "Tofu")];
+        let new: Vec<_> = new_requests(&previous, &current).iter().map(|r| r.user_id.as_str()).collect();
+        assert_eq!(new, vec!["usr_2"]);
+        assert!(new_requests(&current, &previous).is_empty());
+    }
+
+    #[test]
+    fn test_fills_announcement_placeholders() {
+        let text = fill_announcement(
+            "{title} ({category}) {link}",
+            &[("title", "cozy worlds"), ("category", "VRChat"), ("link", "https://twitch.tv/kittyn")],
+        );
+        assert_eq!(text, "cozy worlds (VRChat) https://twitch.tv/kittyn");
+    }
+}
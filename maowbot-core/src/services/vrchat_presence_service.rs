@@ -0,0 +1,308 @@
+// File: maowbot-core/src/services/vrchat_presence_service.rs
+//
+// Who is in the streamer's VRChat instance. VRChat only tells us where our
+// friends are, so every `vrchat.presence.poll_seconds` the online friends
+// list is compared with the streamer's own location. Friends arriving or
+// leaving while the streamer stays put are published as
+// `BotEvent::VRChatPresence` (the PluginManager forwards them to plugins as a
+// `vrchat_presence` GameEvent for the overlay). Friends on the watch list
+// also flip an avatar parameter and, if enabled, get announced in the
+// chatbox. `!whosHere` answers from the last poll.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+use maowbot_osc::vrchat::chatbox::{send_chatbox_message, ChatboxMessage};
+use maowbot_osc::MaowOscManager;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::vrchat::active_credential;
+use crate::platforms::vrchat::client::{is_session_expired, VRChatClient, VRChatFriend};
+use crate::platforms::vrchat::world_cache::{world_cache, CacheTtls};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// A friend in the streamer's instance.
+#[derive(Debug, Clone)]
+pub struct PresentFriend {
+    pub user_id: String,
+    pub display_name: String,
+    /// When we first saw them here (the poll that found them)
+    pub since: DateTime<Utc>,
+    pub watched: bool,
+}
+
+/// The streamer's instance as of the last poll.
+#[derive(Debug, Clone, Default)]
+pub struct PresenceSnapshot {
+    /// None while offline or in a hidden instance
+    pub location: Option<String>,
+    pub world_name: Option<String>,
+    pub friends: Vec<PresentFriend>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+/// Whether `friend` is on the comma-separated watch list of display names
+/// and `usr_` ids. `*` watches everyone.
+pub fn is_watched(watch_list: &str, friend: &VRChatFriend) -> bool {
+    watch_list
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            entry == "*"
+                || entry == friend.id
+                || entry.eq_ignore_ascii_case(&friend.display_name)
+        })
+}
+
+/// Friends who arrived in and left `location` between two polls.
+pub fn diff_presence<'a>(
+    previous: &[PresentFriend],
+    online: &'a [VRChatFriend],
+    location: &str,
+) -> (Vec<&'a VRChatFriend>, Vec<PresentFriend>) {
+    let here: Vec<&VRChatFriend> = online.iter().filter(|f| f.location == location).collect();
+    let joined = here.iter()
+        .copied()
+        .filter(|f| !previous.iter().any(|p| p.user_id == f.id))
+        .collect();
+    let left = previous.iter()
+        .filter(|p| !here.iter().any(|f| f.id == p.user_id))
+        .cloned()
+        .collect();
+    (joined, left)
+}
+
+pub struct VRChatPresenceService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    osc_manager: Option<Arc<MaowOscManager>>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    snapshot: Mutex<PresenceSnapshot>,
+}
+
+impl VRChatPresenceService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        osc_manager: Option<Arc<MaowOscManager>>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            credentials_repo,
+            osc_manager,
+            event_bus,
+            settings,
+            snapshot: Mutex::new(PresenceSnapshot::default()),
+        }
+    }
+
+    /// Polls every `vrchat.presence.poll_seconds` while `vrchat.presence.enabled`.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                if service.settings.get_bool("vrchat.presence.enabled").unwrap_or(true) {
+                    if let Err(e) = service.poll().await {
+                        if is_session_expired(&e) {
+                            // The session service reports this; don't repeat it every poll
+                            debug!("[VRChatPresence] {}", e);
+                        } else {
+                            warn!("[VRChatPresence] poll failed: {:?}", e);
+                        }
+                    }
+                }
+                let seconds = service.settings.get_u64("vrchat.presence.poll_seconds").unwrap_or(60).max(30);
+                tokio::select! {
+                    _ = tokio::time::sleep(StdDuration::from_secs(seconds)) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[VRChatPresence] loop stopped");
+        });
+    }
+
+    pub fn snapshot(&self) -> PresenceSnapshot {
+        self.snapshot.lock().clone()
+    }
+
+    /// Checks who is in the streamer's instance now and announces changes.
+    pub async fn poll(&self) -> Result<PresenceSnapshot, Error> {
+        let Some(cred) = active_credential(&*self.credentials_repo, &self.settings).await? else {
+            *self.snapshot.lock() = PresenceSnapshot::default();
+            return Ok(PresenceSnapshot::default());
+        };
+        let client = VRChatClient::new(&cred.primary_token)?;
+        let now = Utc::now();
+
+        let Some(location) = client.fetch_current_location().await? else {
+            let mut snapshot = self.snapshot.lock();
+            *snapshot = PresenceSnapshot { checked_at: Some(now), ..Default::default() };
+            return Ok(snapshot.clone());
+        };
+        let online = client.fetch_online_friends().await?;
+        let watch_list = self.settings.get("vrchat.presence.watch_friends").unwrap_or_default();
+
+        let previous = self.snapshot();
+        if previous.location.as_deref() != Some(location.as_str()) {
+            // The streamer moved (or this is the first poll): whoever is in the
+            // new instance is the baseline, not a wave of joins
+            let world_id = location.split(':').next().unwrap_or_default();
+            let ttl = CacheTtls::from_settings(&self.settings).world;
+            let world_name = match world_cache().world(&client, world_id, ttl).await {
+                Ok(world) => Some(world.name),
+                Err(e) => {
+                    debug!("[VRChatPresence] no world info for {}: {}", world_id, e);
+                    None
+                }
+            };
+            let friends = online.iter()
+                .filter(|f| f.location == location)
+                .map(|f| PresentFriend {
+                    user_id: f.id.clone(),
+                    display_name: f.display_name.clone(),
+                    since: now,
+                    watched: is_watched(&watch_list, f),
+                })
+                .collect();
+            info!("[VRChatPresence] now in {}", world_name.as_deref().unwrap_or(&location));
+            let snapshot = PresenceSnapshot {
+                location: Some(location),
+                world_name,
+                friends,
+                checked_at: Some(now),
+            };
+            *self.snapshot.lock() = snapshot.clone();
+            return Ok(snapshot);
+        }
+
+        let (joined, left) = diff_presence(&previous.friends, &online, &location);
+        let mut friends: Vec<PresentFriend> = previous.friends.iter()
+            .filter(|p| !left.iter().any(|l| l.user_id == p.user_id))
+            .cloned()
+            .collect();
+        for friend in &joined {
+            let watched = is_watched(&watch_list, friend);
+            friends.push(PresentFriend {
+                user_id: friend.id.clone(),
+                display_name: friend.display_name.clone(),
+                since: now,
+                watched,
+            });
+            self.announce(true, &friend.id, &friend.display_name, &location, watched).await;
+        }
+        for friend in &left {
+            self.announce(false, &friend.user_id, &friend.display_name, &location, friend.watched).await;
+        }
+
+        let snapshot = PresenceSnapshot {
+            location: Some(location),
+            world_name: previous.world_name,
+            friends,
+            checked_at: Some(now),
+        };
+        *self.snapshot.lock() = snapshot.clone();
+        Ok(snapshot)
+    }
+
+    async fn announce(&self, joined: bool, user_id: &str, display_name: &str, location: &str, watched: bool) {
+        info!(
+            "[VRChatPresence] {} {} the instance",
+            display_name,
+            if joined { "joined" } else { "left" }
+        );
+        self.event_bus.publish(BotEvent::VRChatPresence {
+            joined,
+            user_id: user_id.to_string(),
+            display_name: display_name.to_string(),
+            location: location.to_string(),
+            watched,
+            timestamp: Utc::now(),
+        }).await;
+
+        if joined && watched {
+            self.notify_watched_join(display_name);
+        }
+    }
+
+    /// Pulses `vrchat.presence.osc_parameter` and, if enabled, says hi in the chatbox.
+    fn notify_watched_join(&self, display_name: &str) {
+        let Some(osc) = self.osc_manager.clone() else {
+            return;
+        };
+        if let Some(param) = self.settings.get("vrchat.presence.osc_parameter").filter(|p| !p.trim().is_empty()) {
+            let seconds = self.settings.get_u64("vrchat.presence.osc_pulse_seconds").unwrap_or(5).max(1);
+            if let Err(e) = osc.send_avatar_parameter_bool(&param, true) {
+                debug!("[VRChatPresence] OSC send failed: {:?}", e);
+            }
+            let osc = osc.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(StdDuration::from_secs(seconds)).await;
+                let _ = osc.send_avatar_parameter_bool(&param, false);
+            });
+        }
+        if self.settings.get_bool("vrchat.presence.chatbox").unwrap_or(false) {
+            let msg = ChatboxMessage::new(&format!("{} joined!", display_name), true);
+            if let Err(e) = send_chatbox_message(&osc, &msg) {
+                debug!("[VRChatPresence] chatbox send failed: {:?}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn friend(id: &str, name: &str, location: &str) -> VRChatFriend {
+        VRChatFriend {
+            id: id.to_string(),
+            display_name: name.to_string(),
+            location: location.to_string(),
+        }
+    }
+
+    fn present(id: &str, name: &str) -> PresentFriend {
+        PresentFriend {
+            user_id: id.to_string(),
+            display_name: name.to_string(),
+            since: Utc::now(),
+            watched: false,
+        }
+    }
+
+    #[test]
+    fn test_finds_joins_and_leaves_in_the_instance() {
+        let here = "wrld_a:1";
+        let previous = vec![present("usr_1", "Mochi"), present("usr_2", "Tofu")];
+        let online = vec![
+            friend("usr_1", "Mochi", here),
+            friend("usr_2", "Tofu", "wrld_b:9"),
+            friend("usr_3", "Miso", here),
+            friend("usr_4", "Yuzu", "private"),
+        ];
+        let (joined, left) = diff_presence(&previous, &online, here);
+        assert_eq!(joined.iter().map(|f| f.id.as_str()).collect::<Vec<_>>(), vec!["usr_3"]);
+        assert_eq!(left.iter().map(|f| f.user_id.as_str()).collect::<Vec<_>>(), vec!["usr_2"]);
+    }
+
+    #[test]
+    fn test_matches_watch_list_by_name_or_id() {
+        let mochi = friend("usr_1", "Mochi", "wrld_a:1");
+        assert!(is_watched("tofu, mochi", &mochi));
+        assert!(is_watched("usr_1", &mochi));
+        assert!(is_watched("*", &mochi));
+        assert!(!is_watched("", &mochi));
+        assert!(!is_watched("Mochii,usr_2", &mochi));
+    }
+}
@@ -0,0 +1,202 @@
+// File: maowbot-core/src/services/vrchat_session_service.rs
+//
+// Keeps VRChat sessions alive. VRChat has no refresh tokens, and its auth
+// cookie can die well before the 30 days we assume (a password change, a
+// login elsewhere, a 2FA re-check). Every `vrchat.session_check_minutes` each
+// stored VRChat credential is checked against /auth/user: a live session gets
+// its renewed cookie and a later expiry stored, a dead one is reported once
+// through `BotEvent::CredentialRefreshFailed` so the user can `vrchat login`
+// again instead of world/instance commands quietly failing.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::platforms::vrchat::auth::SESSION_DAYS;
+use crate::platforms::vrchat::client::{SessionState, VRChatClient};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// The last check of one VRChat account.
+#[derive(Debug, Clone)]
+pub struct VRChatSessionStatus {
+    pub user_id: Uuid,
+    pub user_name: String,
+    /// None when VRChat couldn't be reached; see `error`.
+    pub state: Option<SessionState>,
+    pub display_name: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub checked_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+pub struct VRChatSessionService {
+    credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    settings: Arc<SettingsRegistry>,
+    statuses: Mutex<HashMap<Uuid, VRChatSessionStatus>>,
+    check_lock: tokio::sync::Mutex<()>,
+}
+
+impl VRChatSessionService {
+    pub fn new(
+        credentials_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+        event_bus: Arc<EventBus>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self {
+            credentials_repo,
+            event_bus,
+            settings,
+            statuses: Mutex::new(HashMap::new()),
+            check_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Checks every session at startup and then every `vrchat.session_check_minutes`.
+    pub fn start(self: &Arc<Self>) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut shutdown_rx = service.event_bus.shutdown_rx.clone();
+            loop {
+                if let Err(e) = service.check_all().await {
+                    warn!("[VRChatSession] check failed: {:?}", e);
+                }
+                let minutes = service.settings.get_u64("vrchat.session_check_minutes").unwrap_or(30).max(5);
+                tokio::select! {
+                    _ = tokio::time::sleep(StdDuration::from_secs(minutes * 60)) => {}
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+            debug!("[VRChatSession] loop stopped");
+        });
+    }
+
+    /// Results of the last check, by account name.
+    pub fn statuses(&self) -> Vec<VRChatSessionStatus> {
+        let mut list: Vec<_> = self.statuses.lock().values().cloned().collect();
+        list.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+        list
+    }
+
+    /// Checks every stored VRChat credential now.
+    pub async fn check_all(&self) -> Result<Vec<VRChatSessionStatus>, Error> {
+        let _guard = self.check_lock.lock().await;
+        let creds = self.credentials_repo.list_credentials_for_platform(&Platform::VRChat).await?;
+        let mut seen = Vec::with_capacity(creds.len());
+        for cred in creds {
+            seen.push(cred.user_id);
+            let status = self.check_one(cred).await;
+            self.statuses.lock().insert(status.user_id, status);
+        }
+        // Accounts removed since the last check
+        self.statuses.lock().retain(|id, _| seen.contains(id));
+        Ok(self.statuses())
+    }
+
+    async fn check_one(&self, cred: PlatformCredential) -> VRChatSessionStatus {
+        let mut status = VRChatSessionStatus {
+            user_id: cred.user_id,
+            user_name: cred.user_name.clone(),
+            state: None,
+            display_name: None,
+            expires_at: cred.expires_at,
+            checked_at: Utc::now(),
+            error: None,
+        };
+        let check = match VRChatClient::new(&cred.primary_token) {
+            Ok(client) => client.check_session().await,
+            Err(e) => Err(e),
+        };
+        let check = match check {
+            Ok(check) => check,
+            Err(e) => {
+                // A network error says nothing about the session; keep the last state
+                status.state = self.statuses.lock().get(&cred.user_id).and_then(|s| s.state);
+                status.error = Some(e.to_string());
+                return status;
+            }
+        };
+        status.state = Some(check.state);
+        status.display_name = check.display_name;
+
+        let previous = self.statuses.lock().get(&cred.user_id).and_then(|s| s.state);
+        match check.state {
+            SessionState::Valid => {
+                let mut renewed = cred;
+                if let Some(cookie) = check.renewed_cookie {
+                    renewed.primary_token = cookie;
+                }
+                renewed.expires_at = Some(Utc::now() + Duration::days(SESSION_DAYS));
+                renewed.updated_at = Utc::now();
+                status.expires_at = renewed.expires_at;
+                if let Err(e) = self.credentials_repo.store_credentials(&renewed).await {
+                    warn!("[VRChatSession] couldn't store the renewed session for {}: {:?}", renewed.user_name, e);
+                }
+                if previous.is_some_and(|s| s != SessionState::Valid) {
+                    info!("[VRChatSession] session for {} is valid again", renewed.user_name);
+                }
+            }
+            state => {
+                let error = session_error(state, &cred.user_name);
+                status.error = Some(error.clone());
+                if should_alert(previous, state) {
+                    warn!("[VRChatSession] {}: {}", cred.user_name, error);
+                    self.event_bus.publish(BotEvent::CredentialRefreshFailed {
+                        credential_id: cred.credential_id,
+                        platform: cred.platform.to_string(),
+                        user_name: cred.user_name.clone(),
+                        expires_at: cred.expires_at,
+                        error,
+                    }).await;
+                }
+            }
+        }
+        status
+    }
+}
+
+/// What the user is told about a session VRChat no longer accepts.
+fn session_error(state: SessionState, user_name: &str) -> String {
+    match state {
+        SessionState::TwoFactorRequired => format!("VRChat wants a 2FA code again; run `vrchat login {}`", user_name),
+        _ => format!("VRChat session expired; run `vrchat login {}`", user_name),
+    }
+}
+
+/// Alert once per change, not on every check while a session stays broken.
+fn should_alert(previous: Option<SessionState>, state: SessionState) -> bool {
+    state != SessionState::Valid && previous != Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alerts_once_per_broken_state() {
+        assert!(should_alert(None, SessionState::Expired));
+        assert!(should_alert(Some(SessionState::Valid), SessionState::TwoFactorRequired));
+        assert!(!should_alert(Some(SessionState::Expired), SessionState::Expired));
+        assert!(should_alert(Some(SessionState::TwoFactorRequired), SessionState::Expired));
+        assert!(!should_alert(Some(SessionState::Expired), SessionState::Valid));
+    }
+
+    #[test]
+    fn test_broken_sessions_point_at_vrchat_login() {
+        assert!(session_error(SessionState::TwoFactorRequired, "maow").contains("2FA"));
+        assert!(session_error(SessionState::Expired, "maow").ends_with("`vrchat login maow`"));
+    }
+}
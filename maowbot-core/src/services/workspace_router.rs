@@ -0,0 +1,122 @@
+// File: maowbot-core/src/services/workspace_router.rs
+//
+// Which workspace answers in a chat channel. Channels are routed to a
+// workspace in `workspace_channels` (`workspace channel add` in the TUI);
+// every other channel belongs to the default workspace. The chat runtimes
+// (commands, redeems) read commands, redeems, config and credentials through
+// the routed workspace's repositories, so two broadcasters on one server each
+// get their own.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use maowbot_common::models::workspace::{channel_key, channel_platform, WorkspaceChannel, DEFAULT_WORKSPACE_ID};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
+
+use crate::repositories::{Repositories, WorkspaceRepos};
+use crate::Error;
+
+/// Channel routes, cached so lookups on every chat line stay off the database.
+pub struct WorkspaceRouter {
+    repos: Repositories,
+    /// (channel_platform, channel_key) => workspace
+    routes: RwLock<HashMap<(String, String), Uuid>>,
+}
+
+impl WorkspaceRouter {
+    pub async fn load(repos: Repositories) -> Result<Self, Error> {
+        let router = Self { repos, routes: RwLock::new(HashMap::new()) };
+        router.reload().await?;
+        Ok(router)
+    }
+
+    /// Re-reads the routes. `assign` and `unassign` call this themselves.
+    pub async fn reload(&self) -> Result<(), Error> {
+        let routes = self.repos.workspaces.list_channels().await?
+            .into_iter()
+            .map(|r| ((r.platform, r.channel), r.workspace_id))
+            .collect();
+        *self.routes.write().unwrap() = routes;
+        Ok(())
+    }
+
+    /// The workspace `channel` is routed to, or the default workspace.
+    pub fn workspace_for(&self, platform: &str, channel: &str) -> Uuid {
+        let key = (channel_platform(platform), channel_key(channel));
+        self.routes.read().unwrap().get(&key).copied().unwrap_or(DEFAULT_WORKSPACE_ID)
+    }
+
+    /// Credentials, config, commands and redeems of the workspace answering in `channel`.
+    pub fn repos_for(&self, platform: &str, channel: &str) -> WorkspaceRepos {
+        self.repos.for_workspace(self.workspace_for(platform, channel))
+    }
+
+    pub fn repos(&self, workspace_id: Uuid) -> WorkspaceRepos {
+        self.repos.for_workspace(workspace_id)
+    }
+
+    /// Credentials of every workspace, for sending as an account picked through `repos_for`.
+    pub fn credentials(&self) -> Arc<dyn CredentialsRepository + Send + Sync> {
+        self.repos.credentials.clone()
+    }
+
+    /// Every workspace, default included.
+    pub async fn workspace_ids(&self) -> Result<Vec<Uuid>, Error> {
+        Ok(self.repos.workspaces.list_workspaces().await?.into_iter().map(|w| w.workspace_id).collect())
+    }
+
+    pub async fn routes(&self) -> Result<Vec<WorkspaceChannel>, Error> {
+        self.repos.workspaces.list_channels().await
+    }
+
+    pub async fn assign(&self, workspace_id: Uuid, platform: &str, channel: &str) -> Result<(), Error> {
+        self.repos.workspaces.assign_channel(workspace_id, platform, channel).await?;
+        self.reload().await
+    }
+
+    /// Sends `channel` back to the default workspace. Returns whether it had a route.
+    pub async fn unassign(&self, platform: &str, channel: &str) -> Result<bool, Error> {
+        let removed = self.repos.workspaces.unassign_channel(platform, channel).await?;
+        self.reload().await?;
+        Ok(removed)
+    }
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use maowbot_common::models::workspace::Workspace;
+    use crate::crypto::Encryptor;
+    use crate::repositories::memory;
+
+    #[tokio::test]
+    async fn test_routed_channels_use_their_workspace() {
+        let db = memory::open_database().await.unwrap();
+        let repos = Repositories::memory(&db, Encryptor::new(&[7u8; 32]).unwrap());
+        let brand = Workspace {
+            workspace_id: Uuid::new_v4(),
+            name: "brand".to_string(),
+            display_name: None,
+            created_at: Utc::now(),
+        };
+        repos.workspaces.create_workspace(&brand).await.unwrap();
+
+        let router = WorkspaceRouter::load(repos).await.unwrap();
+        router.assign(brand.workspace_id, "twitch-irc", "#Brand").await.unwrap();
+
+        // Chat and EventSub name the same Twitch channel
+        assert_eq!(router.workspace_for("twitch-irc", "#brand"), brand.workspace_id);
+        assert_eq!(router.workspace_for("twitch-eventsub", "brand"), brand.workspace_id);
+        assert_eq!(router.workspace_for("twitch-irc", "#maow"), DEFAULT_WORKSPACE_ID);
+        assert_eq!(router.workspace_for("discord", "brand"), DEFAULT_WORKSPACE_ID);
+
+        router.repos_for("twitch-irc", "#brand").bot_config.set_value("chat.prefix", "?").await.unwrap();
+        assert!(router.repos_for("twitch-irc", "#maow").bot_config.get_value("chat.prefix").await.unwrap().is_none());
+
+        assert!(router.unassign("twitch", "BRAND").await.unwrap());
+        assert_eq!(router.workspace_for("twitch-irc", "#brand"), DEFAULT_WORKSPACE_ID);
+        assert!(!router.unassign("twitch", "brand").await.unwrap());
+    }
+}
@@ -0,0 +1,1023 @@
+// File: maowbot-core/src/settings/definitions.rs
+//
+// Every bot_config key the server itself reads. Keys not listed here are still
+// stored and returned as-is (plugins keep their own), just without validation.
+
+use maowbot_common::models::settings::{SettingDefinition, SettingType};
+
+const fn setting(
+    key: &'static str,
+    category: &'static str,
+    setting_type: SettingType,
+    description: &'static str,
+) -> SettingDefinition {
+    SettingDefinition {
+        key,
+        category,
+        description,
+        setting_type,
+        default: None,
+        allowed_values: &[],
+        min: None,
+        max: None,
+        is_secret: false,
+        requires_restart: false,
+    }
+}
+
+pub static SETTINGS: &[SettingDefinition] = &[
+    // general
+    SettingDefinition {
+        min: Some(1),
+        max: Some(65535),
+        requires_restart: true,
+        ..setting("callback_port", "general", SettingType::Integer,
+            "Local port for the OAuth callback server")
+    },
+    setting("autostart", "general", SettingType::Json,
+        "Platform accounts started at boot, as written by `autostart on|off`"),
+
+    // twitch
+    setting("ttv_active_account", "twitch", SettingType::String,
+        "Twitch account the TUI chats as"),
+    setting("ttv_broadcaster_channel", "twitch", SettingType::String,
+        "Broadcaster channel the TUI joins, e.g. #mychannel"),
+    setting("ttv_secondary_account", "twitch", SettingType::String,
+        "Secondary Twitch account (usually the bot) used for replies"),
+    SettingDefinition {
+        default: Some("{}"),
+        ..setting("twitch.account_routing", "twitch", SettingType::Json,
+            "Account each Twitch operation uses: bot, bot_only or broadcaster, e.g. {\"moderation\": \"broadcaster\"}")
+    },
+    SettingDefinition {
+        default: Some("{}"),
+        ..setting("chat.identities", "chat", SettingType::Json,
+            "Account that speaks per platform and channel (\"*\" for the default), e.g. {\"twitch-irc\": {\"mychannel\": \"brandbot\"}}")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("twitch.chatters.enabled", "twitch", SettingType::Boolean,
+            "Track who is in the broadcaster's chat (join/leave events, !lurkers, watchtime)")
+    },
+    SettingDefinition {
+        default: Some("60"),
+        min: Some(30),
+        max: Some(900),
+        ..setting("twitch.chatters.poll_seconds", "twitch", SettingType::Integer,
+            "Seconds between checks of the Twitch chatters list")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(1),
+        max: Some(240),
+        ..setting("twitch.chatters.lurk_minutes", "twitch", SettingType::Integer,
+            "Minutes in chat without talking before a viewer counts as lurking")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("twitch.schedule.enabled", "twitch", SettingType::Boolean,
+            "Watch the Twitch stream schedule for segments starting soon")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(0),
+        max: Some(120),
+        ..setting("twitch.schedule.starting_soon_minutes", "twitch", SettingType::Integer,
+            "Minutes before a scheduled stream to run the starting-soon actions (0 = off)")
+    },
+    setting("twitch.schedule.starting_soon_scene", "twitch", SettingType::String,
+        "OBS scene to switch to when a scheduled stream is starting soon (empty = none)"),
+    SettingDefinition {
+        default: Some("1"),
+        min: Some(1),
+        ..setting("twitch.schedule.obs_instance", "twitch", SettingType::Integer,
+            "OBS instance the starting-soon scene is switched on")
+    },
+    setting("twitch.schedule.starting_soon_message", "twitch", SettingType::String,
+        "Chat announcement when a scheduled stream is starting soon; {title}, {category} and {minutes} are filled in (empty = none)"),
+    SettingDefinition {
+        default: Some("UTC"),
+        ..setting("twitch.schedule.timezone", "twitch", SettingType::String,
+            "IANA time zone new schedule segments are created in, e.g. Europe/Berlin")
+    },
+
+    // vrchat
+    SettingDefinition {
+        default: Some("broadcaster"),
+        ..setting("vrchat_active_account", "vrchat", SettingType::String,
+            "VRChat account used by !world and !instance")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(5),
+        max: Some(1440),
+        ..setting("vrchat.session_check_minutes", "vrchat", SettingType::Integer,
+            "Minutes between VRChat session checks (keeps logins alive, alerts when they expire)")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("vrchat.presence.enabled", "vrchat", SettingType::Boolean,
+            "Track which friends are in the streamer's VRChat instance (!whosHere, join/leave events)")
+    },
+    SettingDefinition {
+        default: Some("60"),
+        min: Some(30),
+        max: Some(900),
+        ..setting("vrchat.presence.poll_seconds", "vrchat", SettingType::Integer,
+            "Seconds between checks of the VRChat friends list")
+    },
+    setting("vrchat.presence.watch_friends", "vrchat", SettingType::String,
+        "Friends whose joins trigger OSC/chatbox notifications (display names or usr_ ids, comma-separated; * for all)"),
+    setting("vrchat.presence.osc_parameter", "vrchat", SettingType::String,
+        "Bool avatar parameter switched on when a watched friend joins (blank to skip)"),
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(1),
+        max: Some(60),
+        ..setting("vrchat.presence.osc_pulse_seconds", "vrchat", SettingType::Integer,
+            "Seconds the watched-friend parameter stays on")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("vrchat.presence.chatbox", "vrchat", SettingType::Boolean,
+            "Announce watched friends joining in the VRChat chatbox")
+    },
+    SettingDefinition {
+        default: Some("60"),
+        min: Some(1),
+        max: Some(1440),
+        ..setting("vrchat.world_cache_minutes", "vrchat", SettingType::Integer,
+            "Minutes VRChat world details (name, author, capacity, platforms) are cached")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(0),
+        max: Some(600),
+        ..setting("vrchat.location_cache_seconds", "vrchat", SettingType::Integer,
+            "Seconds the streamer's VRChat location is cached for !world/!instance (0 asks every time)")
+    },
+    setting("vrchat.group.id", "vrchat", SettingType::String,
+        "The streamer's VRChat group (grp_ id) for announcements and join requests"),
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(1),
+        max: Some(120),
+        ..setting("vrchat.group.poll_minutes", "vrchat", SettingType::Integer,
+            "Minutes between checks for new VRChat group join requests")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("vrchat.group.announce_live", "vrchat", SettingType::Boolean,
+            "Post an announcement to the VRChat group when the stream goes live")
+    },
+    SettingDefinition {
+        default: Some("{user} is live!"),
+        ..setting("vrchat.group.announce_title", "vrchat", SettingType::String,
+            "Title of the going-live group post ({user}, {title}, {category}, {link})")
+    },
+    SettingDefinition {
+        default: Some("{title} ({category}) {link}"),
+        ..setting("vrchat.group.announce_text", "vrchat", SettingType::String,
+            "Text of the going-live group post ({user}, {title}, {category}, {link})")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("vrchat.group.announce_notify", "vrchat", SettingType::Boolean,
+            "Send group members a notification with the going-live post")
+    },
+
+    // osc
+    setting("osc_vrchat_dest", "osc", SettingType::String,
+        "Where OSC packets for VRChat are sent (host:port)"),
+    setting("osc_robot_dest", "osc", SettingType::String,
+        "Where OSC packets for the robot controller are sent (host:port)"),
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("osc.relay.enabled", "osc", SettingType::Boolean,
+            "Relay selected Twitch chat lines to the VRChat chatbox")
+    },
+    SettingDefinition {
+        default: Some("mentions,mods,highlighted"),
+        ..setting("osc.relay.filters", "osc", SettingType::String,
+            "Which chat lines are relayed (comma-separated: mentions, mods, highlighted; or all)")
+    },
+    setting("osc.relay.channels", "osc", SettingType::String,
+        "Twitch channels whose chat is relayed (comma-separated; blank for every joined channel)"),
+    SettingDefinition {
+        default: Some("3"),
+        min: Some(2),
+        max: Some(30),
+        ..setting("osc.relay.min_interval_seconds", "osc", SettingType::Integer,
+            "Minimum seconds each relayed line stays in the chatbox (longer lines stay longer)")
+    },
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(1),
+        max: Some(50),
+        ..setting("osc.relay.max_queue", "osc", SettingType::Integer,
+            "Relayed lines waiting for the chatbox before the oldest are dropped")
+    },
+    SettingDefinition {
+        default: Some("12"),
+        min: Some(3),
+        max: Some(25),
+        ..setting("osc.relay.max_name_length", "osc", SettingType::Integer,
+            "Characters of a chatter's name shown before it is cut off")
+    },
+
+    // chat_logging
+    SettingDefinition {
+        default: Some("100"),
+        min: Some(1),
+        max: Some(10_000),
+        requires_restart: true,
+        ..setting("chat_logging.batch_size", "chat_logging", SettingType::Integer,
+            "Number of messages to batch before writing to the database")
+    },
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(1),
+        max: Some(3600),
+        requires_restart: true,
+        ..setting("chat_logging.flush_interval_seconds", "chat_logging", SettingType::Integer,
+            "Maximum seconds between database writes")
+    },
+    SettingDefinition {
+        default: Some("20000"),
+        min: Some(100),
+        max: Some(1_000_000),
+        requires_restart: true,
+        ..setting("chat_logging.max_buffered", "chat_logging", SettingType::Integer,
+            "Messages held while the database is busy; more are dropped instead of slowing the bot down")
+    },
+    SettingDefinition {
+        default: Some("80"),
+        min: Some(1),
+        max: Some(100),
+        requires_restart: true,
+        ..setting("chat_logging.shed_percent", "chat_logging", SettingType::Integer,
+            "How full (percent) the buffer may get before very short and repeated messages are dropped")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(1),
+        max: Some(3650),
+        ..setting("chat_logging.default_retention_days", "chat_logging", SettingType::Integer,
+            "Days chat logs are kept in channels without their own retention policy")
+    },
+
+    // chat_cache
+    SettingDefinition {
+        default: Some("{}"),
+        ..setting("chat_cache.channel_retention", "chat_cache", SettingType::Json,
+            "Per-channel cache limits, e.g. {\"twitch-irc:mychannel\": {\"max_messages\": 500}}")
+    },
+    SettingDefinition {
+        default: Some("2"),
+        min: Some(0),
+        max: Some(24),
+        requires_restart: true,
+        ..setting("chat_cache.warm_start_hours", "chat_cache", SettingType::Integer,
+            "Hours of archived chat loaded into the cache on startup (0 = start empty)")
+    },
+    SettingDefinition {
+        default: Some("5000"),
+        min: Some(0),
+        max: Some(10_000),
+        requires_restart: true,
+        ..setting("chat_cache.warm_start_max_messages", "chat_cache", SettingType::Integer,
+            "Most archived messages loaded into the cache on startup, newest kept")
+    },
+
+    // database
+    SettingDefinition {
+        default: Some("24"),
+        min: Some(0),
+        max: Some(8760),
+        ..setting("db.snapshot_interval_hours", "database", SettingType::Integer,
+            "Hours between scheduled pg_dump snapshots (0 disables them)")
+    },
+    SettingDefinition {
+        default: Some("7"),
+        min: Some(1),
+        max: Some(365),
+        ..setting("db.snapshot_keep", "database", SettingType::Integer,
+            "Number of snapshots kept in postgres/snapshots; older ones are deleted")
+    },
+    SettingDefinition {
+        default: Some("168"),
+        min: Some(0),
+        max: Some(8760),
+        ..setting("db.compact_interval_hours", "database", SettingType::Integer,
+            "Hours between scheduled checkpoint + VACUUM runs (0 disables them)")
+    },
+
+    // retention
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        ..setting("retention.chat_messages_max_rows", "retention", SettingType::Integer,
+            "Most archived chat messages kept; the oldest beyond this are deleted (0 = no cap)")
+    },
+    SettingDefinition {
+        default: Some("90"),
+        min: Some(0),
+        max: Some(3650),
+        ..setting("retention.bot_events_days", "retention", SettingType::Integer,
+            "Days event journal entries are kept (0 = forever)")
+    },
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        ..setting("retention.bot_events_max_rows", "retention", SettingType::Integer,
+            "Most event journal entries kept (0 = no cap)")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(0),
+        max: Some(3650),
+        ..setting("retention.command_usage_days", "retention", SettingType::Integer,
+            "Days command usage records are kept (0 = forever)")
+    },
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        ..setting("retention.command_usage_max_rows", "retention", SettingType::Integer,
+            "Most command usage records kept (0 = no cap)")
+    },
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        max: Some(3650),
+        ..setting("retention.ai_memory_days", "retention", SettingType::Integer,
+            "Days AI memories are kept (0 = until they expire)")
+    },
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        ..setting("retention.ai_memory_max_rows", "retention", SettingType::Integer,
+            "Most AI memories kept (0 = no cap)")
+    },
+
+    // tls
+    setting("tls.extra_sans", "tls", SettingType::String,
+        "Extra host names/IPs for the gRPC certificate, comma separated (e.g. a DNS name or public IP)"),
+    SettingDefinition {
+        default: Some("false"),
+        requires_restart: true,
+        ..setting("tls.require_client_cert", "tls", SettingType::Boolean,
+            "Require clients to present a certificate issued by the local CA (mutual TLS)")
+    },
+
+    // control api
+    SettingDefinition {
+        default: Some("false"),
+        requires_restart: true,
+        ..setting("control_api.enabled", "control_api", SettingType::Boolean,
+            "Serve the HTTP control API used by Stream Deck and similar tools")
+    },
+    SettingDefinition {
+        default: Some("127.0.0.1:7717"),
+        requires_restart: true,
+        ..setting("control_api.address", "control_api", SettingType::String,
+            "Address the control API listens on; keep it on localhost unless the network is trusted (plain HTTP)")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("alerts.muted", "alerts", SettingType::Boolean,
+            "Mark new alerts as muted so overlays and the GUI show them silently")
+    },
+
+    // updater
+    setting("updater.feed_url", "updater", SettingType::String,
+        "https URL of the JSON release feed checked for new server builds"),
+    setting("updater.public_key", "updater", SettingType::String,
+        "Base64 Ed25519 public key releases (version, target and digest) must be signed with"),
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("updater.auto_update", "updater", SettingType::Boolean,
+            "Install new releases automatically and restart into them")
+    },
+    SettingDefinition {
+        default: Some("24"),
+        min: Some(1),
+        max: Some(720),
+        ..setting("updater.check_interval_hours", "updater", SettingType::Integer,
+            "Hours between scheduled release checks")
+    },
+
+    // social
+    SettingDefinition {
+        default: Some("https://bsky.social"),
+        ..setting("social.bluesky.service", "social", SettingType::String,
+            "Bluesky PDS the account lives on")
+    },
+    setting("social.bluesky.handle", "social", SettingType::String,
+        "Bluesky handle or DID posts are made as, e.g. me.bsky.social"),
+    SettingDefinition {
+        is_secret: true,
+        ..setting("social.bluesky.app_password", "social", SettingType::String,
+            "Bluesky app password (Settings → Privacy and security → App passwords)")
+    },
+    setting("social.mastodon.instance", "social", SettingType::String,
+        "Mastodon instance URL, e.g. https://mastodon.social"),
+    SettingDefinition {
+        is_secret: true,
+        ..setting("social.mastodon.access_token", "social", SettingType::String,
+            "Mastodon access token with the write:statuses scope")
+    },
+    SettingDefinition {
+        default: Some("public"),
+        allowed_values: &["public", "unlisted", "private"],
+        ..setting("social.mastodon.visibility", "social", SettingType::Enum,
+            "Visibility of statuses posted to Mastodon")
+    },
+
+    // donations
+    SettingDefinition {
+        is_secret: true,
+        ..setting("donations.streamlabs.socket_token", "donations", SettingType::String,
+            "Streamlabs socket API token (Settings → API Tokens)")
+    },
+    SettingDefinition {
+        is_secret: true,
+        ..setting("donations.streamelements.jwt", "donations", SettingType::String,
+            "StreamElements JWT token (Account → Channels → Show secrets)")
+    },
+    SettingDefinition {
+        default: Some("USD"),
+        ..setting("donations.base_currency", "donations", SettingType::String,
+            "Currency leaderboards and TTS thresholds are counted in")
+    },
+    SettingDefinition {
+        default: Some("{}"),
+        ..setting("donations.exchange_rates", "donations", SettingType::Json,
+            "Value of one unit of each currency in the base currency, e.g. {\"EUR\": 1.08, \"GBP\": 1.27}")
+    },
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        ..setting("donations.tts_min_amount", "donations", SettingType::Integer,
+            "Smallest donation (in the base currency) whose message is read by TTS")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(0),
+        max: Some(3650),
+        ..setting("donations.leaderboard_days", "donations", SettingType::Integer,
+            "Days the donation leaderboard covers (0 = all time)")
+    },
+
+    // midi
+    setting("midi.device", "midi", SettingType::String,
+        "MIDI input port to read, matched by (part of) its name, e.g. nanoKONTROL2 (blank turns MIDI input off)"),
+    SettingDefinition {
+        default: Some("3"),
+        min: Some(0),
+        max: Some(3600),
+        ..setting("midi.chat_cooldown_seconds", "midi", SettingType::Integer,
+            "Seconds before the same MIDI chat macro can be sent again")
+    },
+
+    // heart rate
+    SettingDefinition {
+        default: Some("off"),
+        allowed_values: &["off", "pulsoid", "hyperate"],
+        ..setting("heart_rate.source", "heart_rate", SettingType::Enum,
+            "Where live heart rate is read from")
+    },
+    SettingDefinition {
+        is_secret: true,
+        ..setting("heart_rate.pulsoid.token", "heart_rate", SettingType::String,
+            "Pulsoid access token with the data:heart_rate:read scope")
+    },
+    SettingDefinition {
+        is_secret: true,
+        ..setting("heart_rate.hyperate.api_key", "heart_rate", SettingType::String,
+            "HypeRate developer API key")
+    },
+    setting("heart_rate.hyperate.session_id", "heart_rate", SettingType::String,
+        "Session id shown in the HypeRate app"),
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("heart_rate.osc.enabled", "heart_rate", SettingType::Boolean,
+            "Forward heart rate to VRChat avatar parameters")
+    },
+    SettingDefinition {
+        default: Some("HR"),
+        ..setting("heart_rate.osc.parameter", "heart_rate", SettingType::String,
+            "Int avatar parameter set to the BPM (blank to skip)")
+    },
+    SettingDefinition {
+        default: Some("HRPercent"),
+        ..setting("heart_rate.osc.percent_parameter", "heart_rate", SettingType::String,
+            "Float avatar parameter set to BPM / max_bpm (blank to skip)")
+    },
+    SettingDefinition {
+        default: Some("isHRConnected"),
+        ..setting("heart_rate.osc.connected_parameter", "heart_rate", SettingType::String,
+            "Bool avatar parameter that is true while readings arrive (blank to skip)")
+    },
+    SettingDefinition {
+        default: Some("200"),
+        min: Some(40),
+        max: Some(300),
+        ..setting("heart_rate.osc.max_bpm", "heart_rate", SettingType::Integer,
+            "BPM that maps to 1.0 on the percent parameter")
+    },
+
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("markers.enabled", "markers", SettingType::Boolean,
+            "Place Twitch stream markers automatically and export VOD chapters at stream end")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("markers.on_raid", "markers", SettingType::Boolean,
+            "Place a marker when a raid arrives")
+    },
+    SettingDefinition {
+        default: Some("5000"),
+        min: Some(0),
+        ..setting("markers.redeem_min_cost", "markers", SettingType::Integer,
+            "Place a marker for channel point redeems costing at least this much (0 = never)")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("markers.on_scene_change", "markers", SettingType::Boolean,
+            "Place a marker when the OBS scene changes")
+    },
+
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("hype.enabled", "hype", SettingType::Boolean,
+            "Detect hype moments from chat speed, emote spam, bits and subs while live")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(10),
+        max: Some(300),
+        ..setting("hype.window_seconds", "hype", SettingType::Integer,
+            "Seconds of activity a hype moment is judged on")
+    },
+    SettingDefinition {
+        default: Some("180"),
+        min: Some(30),
+        max: Some(3600),
+        ..setting("hype.cooldown_seconds", "hype", SettingType::Integer,
+            "Seconds after a hype moment before the next one can be detected")
+    },
+    SettingDefinition {
+        default: Some("25"),
+        min: Some(0),
+        ..setting("hype.min_messages", "hype", SettingType::Integer,
+            "Chat lines in the window needed for a chat surge, however quiet chat usually is (0 = ignore chat speed)")
+    },
+    SettingDefinition {
+        default: Some("300"),
+        min: Some(100),
+        max: Some(2000),
+        ..setting("hype.chat_surge_percent", "hype", SettingType::Integer,
+            "Chat speed, as a percentage of its usual speed, that counts as a surge")
+    },
+    SettingDefinition {
+        default: Some("15"),
+        min: Some(0),
+        ..setting("hype.emote_messages", "hype", SettingType::Integer,
+            "Mostly-emote chat lines in the window that count as an emote burst (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("500"),
+        min: Some(0),
+        ..setting("hype.bits", "hype", SettingType::Integer,
+            "Bits cheered within the window that count as a hype moment (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(0),
+        ..setting("hype.subs", "hype", SettingType::Integer,
+            "Subs, resubs and gifted subs within the window that count as a hype moment (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("hype.create_marker", "hype", SettingType::Boolean,
+            "Place a stream marker on each hype moment")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("hype.save_replay", "hype", SettingType::Boolean,
+            "Save the OBS replay buffer on each hype moment (the replay buffer must be running)")
+    },
+    SettingDefinition {
+        default: Some("1"),
+        min: Some(1),
+        ..setting("hype.obs_instance", "hype", SettingType::Integer,
+            "OBS instance whose replay buffer is saved")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("giveaways.announce_chat", "giveaways", SettingType::Boolean,
+            "Announce giveaway openings, closings and winners in the giveaway's Twitch chat")
+    },
+    setting("giveaways.discord_account", "giveaways", SettingType::String,
+        "Discord account that announces giveaways (blank to skip Discord)"),
+    setting("giveaways.discord_guild_id", "giveaways", SettingType::String,
+        "Discord server for giveaway announcements"),
+    setting("giveaways.discord_channel_id", "giveaways", SettingType::String,
+        "Discord channel id or name for giveaway announcements"),
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("crowd.announce_chat", "crowd", SettingType::Boolean,
+            "Announce crowd votes and their winning effect in the vote's Twitch chat")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(0),
+        max: Some(10000),
+        ..setting("crowd.weight_hours", "crowd", SettingType::Integer,
+            "Hours of watchtime that earn a viewer one extra vote weight (0 = every vote counts once)")
+    },
+    SettingDefinition {
+        default: Some("3"),
+        min: Some(1),
+        max: Some(100),
+        ..setting("crowd.max_weight", "crowd", SettingType::Integer,
+            "Most a single viewer's vote can weigh")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("protection.enabled", "protection", SettingType::Boolean,
+            "Lock chat down automatically when a raid-like spike is detected")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(2),
+        max: Some(120),
+        ..setting("protection.window_secs", "protection", SettingType::Integer,
+            "Seconds of chat the spike heuristic looks at")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(0),
+        ..setting("protection.messages_per_sec", "protection", SettingType::Integer,
+            "Average messages per second over the window that counts as a spike (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("8"),
+        min: Some(0),
+        ..setting("protection.identical_messages", "protection", SettingType::Integer,
+            "Different chatters sending the same message within the window that counts as a spike (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("7"),
+        min: Some(1),
+        ..setting("protection.new_account_days", "protection", SettingType::Integer,
+            "Accounts younger than this many days count as new")
+    },
+    SettingDefinition {
+        default: Some("60"),
+        min: Some(0),
+        max: Some(100),
+        ..setting("protection.new_account_percent", "protection", SettingType::Integer,
+            "Percentage of new accounts among recent chatters that counts as a spike (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(1),
+        ..setting("protection.min_chatters", "protection", SettingType::Integer,
+            "Chatters needed in the window before the new-account check applies")
+    },
+    SettingDefinition {
+        default: Some("0"),
+        min: Some(0),
+        max: Some(100),
+        ..setting("protection.likely_bot_percent", "protection", SettingType::Integer,
+            "Percentage of likely bots among scored recent chatters that counts as a spike (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("70"),
+        min: Some(1),
+        max: Some(100),
+        ..setting("protection.likely_bot_score", "protection", SettingType::Integer,
+            "Bot score (percent) at which a chatter counts as a likely bot")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("protection.followers_only", "protection", SettingType::Boolean,
+            "Turn on follower-only mode during a lockdown")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(0),
+        max: Some(129600),
+        ..setting("protection.follower_only_minutes", "protection", SettingType::Integer,
+            "Minutes viewers must have followed to chat during a lockdown")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(0),
+        max: Some(120),
+        ..setting("protection.slow_mode_secs", "protection", SettingType::Integer,
+            "Slow mode delay during a lockdown in seconds (0 = leave slow mode alone)")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("protection.shield_mode", "protection", SettingType::Boolean,
+            "Also turn on Twitch Shield Mode during a lockdown")
+    },
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(0),
+        ..setting("protection.calm_minutes", "protection", SettingType::Integer,
+            "Lift a lockdown after this many minutes without a spike (0 = only by hand)")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("protection.announce", "protection", SettingType::Boolean,
+            "Tell chat when a lockdown starts and ends")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("bot_detection.enabled", "bot_detection", SettingType::Boolean,
+            "Score new Twitch chatters for how likely they are bot accounts")
+    },
+    SettingDefinition {
+        default: Some("3"),
+        min: Some(1),
+        max: Some(20),
+        ..setting("bot_detection.sample_messages", "bot_detection", SettingType::Integer,
+            "Messages to wait for before scoring a new chatter (scored after two minutes regardless)")
+    },
+    SettingDefinition {
+        default: Some("24"),
+        min: Some(1),
+        max: Some(720),
+        ..setting("bot_detection.new_chatter_hours", "bot_detection", SettingType::Integer,
+            "Chatters first seen by the bot within this many hours count as new")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("moderation.enabled", "moderation", SettingType::Boolean,
+            "Enforce link and phrase moderation rules in the broadcaster's chat")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("moderation.warn_in_chat", "moderation", SettingType::Boolean,
+            "Tell the chatter which rule they broke")
+    },
+    SettingDefinition {
+        default: Some("warn, timeout:600, ban"),
+        ..setting("moderation.escalation_ladder", "moderation", SettingType::String,
+            "What repeat offenses in a rule category get, in order: warn, timeout:SECS or ban")
+    },
+    SettingDefinition {
+        default: Some("90"),
+        min: Some(0),
+        max: Some(3650),
+        ..setting("moderation.escalation_window_days", "moderation", SettingType::Integer,
+            "Days an offense counts toward the escalation ladder (0 = forever)")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("milestones.enabled", "milestones", SettingType::Boolean,
+            "Celebrate sub anniversaries, sub streaks and follow anniversaries in the broadcaster's chat")
+    },
+    SettingDefinition {
+        default: Some("3, 6, 12, 24, 36"),
+        ..setting("milestones.streak_months", "milestones", SettingType::String,
+            "Sub streaks, in months, that get celebrated when a viewer shares their resub")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("milestones.follow_anniversaries", "milestones", SettingType::Boolean,
+            "Celebrate a year of following on the viewer's next chat message")
+    },
+    SettingDefinition {
+        default: Some("7"),
+        min: Some(0),
+        max: Some(60),
+        ..setting("milestones.follow_grace_days", "milestones", SettingType::Integer,
+            "Days after a follow anniversary that the viewer's first chat message still celebrates it")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("milestones.chat", "milestones", SettingType::Boolean,
+            "Congratulate the viewer in chat")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("milestones.overlay", "milestones", SettingType::Boolean,
+            "Send celebrations to overlays as a milestone event")
+    },
+    setting("milestones.osc_parameter", "milestones", SettingType::String,
+        "Bool avatar parameter to turn on for a celebration, e.g. a confetti toggle (empty = off)"),
+    SettingDefinition {
+        default: Some("5"),
+        min: Some(1),
+        max: Some(60),
+        ..setting("milestones.osc_seconds", "milestones", SettingType::Integer,
+            "Seconds the celebration's avatar parameter stays on")
+    },
+    SettingDefinition {
+        default: Some("whisper"),
+        allowed_values: &["whisper", "chat", "silent"],
+        ..setting("commands.cooldown_feedback", "commands", SettingType::Enum,
+            "How a chatter hears that a command is on cooldown: whispered, in chat, or not at all")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("commands.link_enabled", "commands", SettingType::Boolean,
+            "Let viewers link their Twitch and Discord accounts themselves with !link codes")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(0),
+        max: Some(3600),
+        ..setting("outbound.dedupe_seconds", "outbound", SettingType::Integer,
+            "Seconds during which the bot won't send the exact same message to a channel again (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("20"),
+        min: Some(0),
+        ..setting("outbound.max_messages", "outbound", SettingType::Integer,
+            "Messages the bot may send to one channel per outbound.window_seconds (0 = no limit)")
+    },
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(1),
+        max: Some(300),
+        ..setting("outbound.window_seconds", "outbound", SettingType::Integer,
+            "Length of the outbound rate window in seconds")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("outbound.ignore_own_messages", "outbound", SettingType::Boolean,
+            "Never run commands for messages sent by the bot's own accounts")
+    },
+    SettingDefinition {
+        default: Some(crate::services::outbound_chain::DEFAULT_CHAIN),
+        ..setting("outbound.middleware", "outbound", SettingType::String,
+            "Middleware every outgoing chat line runs through, in order: templates, profanity, shorten_links, split, log (or none)")
+    },
+    setting("outbound.middleware.twitch-irc", "outbound", SettingType::String,
+        "Outbound middleware for Twitch chat (blank = outbound.middleware)"),
+    setting("outbound.middleware.discord", "outbound", SettingType::String,
+        "Outbound middleware for Discord (blank = outbound.middleware)"),
+    setting("outbound.middleware.kick", "outbound", SettingType::String,
+        "Outbound middleware for Kick chat (blank = outbound.middleware)"),
+    setting("outbound.profanity_words", "outbound", SettingType::String,
+        "Comma-separated words the profanity middleware masks with asterisks"),
+    setting("outbound.shortener_url", "outbound", SettingType::String,
+        "Link shortener endpoint for the shorten_links middleware; {url} is replaced with the long URL and the response body is the short link"),
+    SettingDefinition {
+        default: Some("60"),
+        min: Some(10),
+        max: Some(2000),
+        ..setting("outbound.shorten_min_length", "outbound", SettingType::Integer,
+            "URLs at least this long are shortened by the shorten_links middleware")
+    },
+
+    // ai
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("ai.safety.redact_pii", "ai", SettingType::Boolean,
+            "Replace emails, phone numbers, card numbers and IP addresses in AI answers with [redacted]")
+    },
+    setting("ai.safety.profanity_words", "ai", SettingType::String,
+        "Comma-separated words masked with asterisks in AI answers"),
+    setting("ai.safety.family_friendly_channels", "ai", SettingType::String,
+        "Channels whose AI answers also mask common swears and withhold answers that need many masks (comma-separated; * for all)"),
+    setting("ai.safety.withheld_message", "ai", SettingType::String,
+        "Sent instead of a withheld AI answer (blank for the default)"),
+    SettingDefinition {
+        default: Some("4"),
+        min: Some(1),
+        max: Some(10),
+        ..setting("ai.safety.max_chunks", "ai", SettingType::Integer,
+            "Most chat messages one AI answer is split into; the rest is cut off")
+    },
+    SettingDefinition {
+        default: Some("300"),
+        min: Some(50),
+        max: Some(2000),
+        ..setting("ai.safety.tts_max_chars", "ai", SettingType::Integer,
+            "Characters of an AI answer passed to TTS")
+    },
+    SettingDefinition {
+        default: Some("false"),
+        ..setting("ai.summary.enabled", "ai", SettingType::Boolean,
+            "Summarize each channel's chat into the AI's memory as the stream goes on (uses AI requests)")
+    },
+    SettingDefinition {
+        default: Some("15"),
+        min: Some(5),
+        max: Some(240),
+        ..setting("ai.summary.interval_minutes", "ai", SettingType::Integer,
+            "Minutes between chat summaries")
+    },
+    SettingDefinition {
+        default: Some("20"),
+        min: Some(5),
+        max: Some(400),
+        ..setting("ai.summary.min_messages", "ai", SettingType::Integer,
+            "New chat lines a channel needs before it is summarized")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("emotes.tracking_enabled", "emotes", SettingType::Boolean,
+            "Count emote usage in Twitch chat for !emotestats and the analytics tab")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("emotes.third_party", "emotes", SettingType::Boolean,
+            "Fetch 7TV, BTTV and FFZ emotes from their public APIs for emote stats, completion and chat")
+    },
+    setting("emotes.channels", "emotes", SettingType::String,
+        "Comma-separated Twitch channels whose 7TV/BTTV/FFZ emotes are kept synced (blank for the broadcaster's)"),
+    SettingDefinition {
+        default: Some("30"),
+        min: Some(5),
+        max: Some(1440),
+        ..setting("emotes.refresh_minutes", "emotes", SettingType::Integer,
+            "Minutes between refetches of third-party emote sets; 7TV changes also arrive live")
+    },
+    setting("clips.discord_account", "clips", SettingType::String,
+        "Discord account that posts new Twitch clips (blank to skip Discord)"),
+    setting("clips.discord_guild_id", "clips", SettingType::String,
+        "Discord server for clip posts"),
+    setting("clips.discord_channel_id", "clips", SettingType::String,
+        "Discord channel id or name for clip posts"),
+    SettingDefinition {
+        default: Some("120"),
+        min: Some(30),
+        max: Some(3600),
+        ..setting("clips.poll_interval_seconds", "clips", SettingType::Integer,
+            "Seconds between checks for new clips of the broadcaster")
+    },
+    SettingDefinition {
+        default: Some("en"),
+        ..setting("i18n.default_language", "i18n", SettingType::String,
+            "Language of bot responses in channels without their own (e.g. en, es, de, pt-br)")
+    },
+    SettingDefinition {
+        default: Some("60"),
+        min: Some(15),
+        max: Some(3600),
+        ..setting("redeems.schedule_check_seconds", "redeems", SettingType::Integer,
+            "Seconds between checks of redeem schedules (time windows apply within this delay)")
+    },
+    SettingDefinition {
+        default: Some("300"),
+        min: Some(0),
+        max: Some(86400),
+        ..setting("redeems.approval_timeout_seconds", "redeems", SettingType::Integer,
+            "Seconds a redemption waits for approval before it's refunded (0 = wait forever)")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("responders.enabled", "responders", SettingType::Boolean,
+            "Answer chat lines that match a responder's regex or keywords")
+    },
+    SettingDefinition {
+        default: Some("true"),
+        ..setting("alerting.enabled", "alerting", SettingType::Boolean,
+            "Raise alerts for disconnects, credential failures, failing pipelines and low disk")
+    },
+    SettingDefinition {
+        default: Some("UTC"),
+        ..setting("alerting.timezone", "alerting", SettingType::String,
+            "Time zone alert rule quiet hours are in, e.g. Europe/Berlin")
+    },
+    setting("alerting.discord_account", "alerting", SettingType::String,
+        "Discord account that sends alert DMs (blank = the Discord bot credential)"),
+    SettingDefinition {
+        default: Some("3"),
+        min: Some(1),
+        max: Some(100),
+        ..setting("alerting.reconnect_attempts", "alerting", SettingType::Integer,
+            "Reconnect attempts before a dropped platform connection raises an alert")
+    },
+    SettingDefinition {
+        default: Some("15"),
+        min: Some(1),
+        max: Some(1440),
+        ..setting("alerting.pipeline_window_minutes", "alerting", SettingType::Integer,
+            "Minutes of pipeline history counted for the failed-executions alert")
+    },
+    SettingDefinition {
+        default: Some("10"),
+        min: Some(0),
+        max: Some(100000),
+        ..setting("alerting.pipeline_failures", "alerting", SettingType::Integer,
+            "Failed pipeline executions within the window that raise an alert (0 = off)")
+    },
+    SettingDefinition {
+        default: Some("2048"),
+        min: Some(0),
+        max: Some(10485760),
+        ..setting("alerting.disk_min_free_mb", "alerting", SettingType::Integer,
+            "Free disk space in MB below which an alert is raised (0 = off)")
+    },
+    setting("alerting.disk_path", "alerting", SettingType::String,
+        "Directory whose disk is watched for free space (blank = the bot's working directory)"),
+];
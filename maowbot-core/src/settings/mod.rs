@@ -0,0 +1,329 @@
+// File: maowbot-core/src/settings/mod.rs
+//
+// Typed view over the bot_config table. Values are cached in memory, validated
+// against `definitions::SETTINGS` on write, and reloaded in the background so a
+// change made anywhere (gRPC, plugins, another process) reaches every service
+// without a restart. Each change is published as `BotEvent::ConfigChanged`.
+// Settings marked `is_secret` are stored encrypted (`set_secret`) and read back
+// with `get_secret`.
+
+pub mod definitions;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use maowbot_common::models::settings::{parse_bool, SettingDefinition};
+use maowbot_common::traits::repository_traits::BotConfigRepository;
+use crate::eventbus::{BotEvent, EventBus};
+use crate::Error;
+
+pub use definitions::SETTINGS;
+
+/// Shown instead of secret values in events.
+const REDACTED: &str = "********";
+
+/// Called with (old, new) after a key changes; `None` means unset.
+pub type SettingWatcher = Arc<dyn Fn(Option<&str>, Option<&str>) + Send + Sync>;
+
+pub struct SettingsRegistry {
+    repo: Arc<dyn BotConfigRepository + Send + Sync>,
+    event_bus: Arc<EventBus>,
+    values: RwLock<HashMap<String, String>>,
+    watchers: RwLock<HashMap<String, Vec<SettingWatcher>>>,
+    /// Serializes reloads so an older read can't overwrite a newer one
+    reload_lock: tokio::sync::Mutex<()>,
+}
+
+impl SettingsRegistry {
+    pub fn new(repo: Arc<dyn BotConfigRepository + Send + Sync>, event_bus: Arc<EventBus>) -> Self {
+        Self {
+            repo,
+            event_bus,
+            values: RwLock::new(HashMap::new()),
+            watchers: RwLock::new(HashMap::new()),
+            reload_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn definitions() -> &'static [SettingDefinition] {
+        SETTINGS
+    }
+
+    pub fn definition(key: &str) -> Option<&'static SettingDefinition> {
+        SETTINGS.iter().find(|d| d.key == key)
+    }
+
+    /// Whether `key` is a secret setting, stored encrypted.
+    pub fn is_secret(key: &str) -> bool {
+        Self::definition(key).is_some_and(|d| d.is_secret)
+    }
+
+    /// Validates a value for `key`. Unknown keys are accepted unchanged.
+    pub fn validate(key: &str, value: &str) -> Result<(), Error> {
+        match Self::definition(key) {
+            Some(def) => def.validate(value),
+            None => Ok(()),
+        }
+    }
+
+    /// Fills the cache from the database without publishing events.
+    pub async fn load(&self) -> Result<(), Error> {
+        let rows = self.read_all().await?;
+        for def in SETTINGS {
+            if let Some(value) = rows.get(def.key) {
+                if let Err(e) = def.validate(value) {
+                    warn!("Stored value for '{}' is invalid, using the default: {}", def.key, e);
+                }
+            }
+        }
+        *self.values.write() = rows;
+        Ok(())
+    }
+
+    /// Every stored setting, secrets decrypted. A secret setting still stored
+    /// in plain text (by an older version, or written around the registry) is
+    /// encrypted in place first.
+    async fn read_all(&self) -> Result<HashMap<String, String>, Error> {
+        let mut rows: HashMap<String, String> = self.repo.list_all().await?.into_iter().collect();
+        for def in SETTINGS.iter().filter(|d| d.is_secret) {
+            if let Some(plain) = rows.remove(def.key) {
+                info!("Encrypting the stored value of secret setting '{}'", def.key);
+                self.repo.set_secret(def.key, &plain).await?;
+            }
+        }
+        for key in self.repo.list_secret_keys().await? {
+            if Self::is_secret(&key) {
+                if let Some(value) = self.repo.get_secret(&key).await? {
+                    rows.insert(key, value);
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// The stored value, or the setting's default.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.get_stored(key)
+            .or_else(|| Self::definition(key).and_then(|d| d.default).map(String::from))
+    }
+
+    /// The stored value only; `None` when the key is unset.
+    pub fn get_stored(&self, key: &str) -> Option<String> {
+        self.values.read().get(key).cloned()
+    }
+
+    pub fn is_set(&self, key: &str) -> bool {
+        self.values.read().contains_key(key)
+    }
+
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get_typed(key, |v| {
+            let n = v.trim().parse::<i64>().ok()?;
+            Self::validate(key, v).ok().map(|_| n)
+        })
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get_i64(key).and_then(|n| u64::try_from(n).ok())
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get_typed(key, parse_bool)
+    }
+
+    pub fn get_json<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.get_typed(key, |v| serde_json::from_str(v).ok())
+    }
+
+    /// Parses the stored value, falling back to the default when it is unset or invalid.
+    fn get_typed<T>(&self, key: &str, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+        if let Some(stored) = self.get_stored(key) {
+            match parse(&stored) {
+                Some(v) => return Some(v),
+                None => debug!("Ignoring invalid value for '{}': {}", key, stored),
+            }
+        }
+        Self::definition(key).and_then(|d| d.default).and_then(parse)
+    }
+
+    /// Validates and stores a value, then applies it immediately.
+    pub async fn set(&self, key: &str, value: &str) -> Result<(), Error> {
+        Self::validate(key, value)?;
+        if Self::is_secret(key) {
+            self.repo.set_secret(key, value).await?;
+        } else {
+            self.repo.set_value(key, value).await?;
+        }
+        self.refresh().await?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), Error> {
+        self.repo.delete_value(key).await?;
+        self.refresh().await?;
+        Ok(())
+    }
+
+    /// Re-reads bot_config; every changed key notifies its watchers and is
+    /// published as `ConfigChanged`. Returns the number of changed keys.
+    pub async fn refresh(&self) -> Result<usize, Error> {
+        let _reload = self.reload_lock.lock().await;
+        let fresh = self.read_all().await?;
+
+        let changes: Vec<(String, Option<String>, Option<String>)> = {
+            let mut values = self.values.write();
+            let keys: HashSet<&String> = values.keys().chain(fresh.keys()).collect();
+            let mut changes: Vec<_> = keys.into_iter()
+                .filter(|k| values.get(*k) != fresh.get(*k))
+                .map(|k| (k.clone(), values.get(k).cloned(), fresh.get(k).cloned()))
+                .collect();
+            changes.sort_by(|a, b| a.0.cmp(&b.0));
+            *values = fresh;
+            changes
+        };
+
+        for (key, old, new) in &changes {
+            if let (Some(def), Some(value)) = (Self::definition(key), new) {
+                if let Err(e) = def.validate(value) {
+                    warn!("New value for '{}' is invalid, using the default: {}", key, e);
+                }
+                if def.requires_restart {
+                    info!("Setting '{}' changed; it takes effect after a restart", key);
+                }
+            }
+
+            let watchers = self.watchers.read().get(key).cloned().unwrap_or_default();
+            for watcher in watchers {
+                watcher(old.as_deref(), new.as_deref());
+            }
+
+            let secret = Self::is_secret(key);
+            let redact = |v: &Option<String>| {
+                if secret { v.as_ref().map(|_| REDACTED.to_string()) } else { v.clone() }
+            };
+            self.event_bus.publish(BotEvent::ConfigChanged {
+                key: key.clone(),
+                old_value: redact(old),
+                new_value: redact(new),
+                timestamp: Utc::now(),
+            }).await;
+        }
+
+        if !changes.is_empty() {
+            debug!("Settings reloaded: {} key(s) changed", changes.len());
+        }
+        Ok(changes.len())
+    }
+
+    /// Runs `callback` after every change to `key`, from whichever task reloaded it.
+    /// Callbacks must not block; spawn a task for async work.
+    pub fn watch<F>(&self, key: &str, callback: F)
+    where
+        F: Fn(Option<&str>, Option<&str>) + Send + Sync + 'static,
+    {
+        self.watchers.write()
+            .entry(key.to_string())
+            .or_default()
+            .push(Arc::new(callback));
+    }
+
+    /// Re-reads bot_config every `every` so changes written outside the
+    /// registry (plugins, direct SQL) are picked up too.
+    pub fn spawn_reload_task(self: &Arc<Self>, every: Duration) -> JoinHandle<()> {
+        let weak = Arc::downgrade(self);
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(every);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await; // first tick fires immediately
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        let Some(settings) = weak.upgrade() else { break };
+                        if let Err(e) = settings.refresh().await {
+                            error!("Settings reload failed: {:?}", e);
+                        }
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            info!("Settings reload task: shutting down cleanly.");
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_known_and_unknown_keys() {
+        assert!(SettingsRegistry::validate("chat_logging.batch_size", "250").is_ok());
+        assert!(SettingsRegistry::validate("chat_logging.batch_size", "0").is_err());
+        assert!(SettingsRegistry::validate("chat_logging.batch_size", "lots").is_err());
+        assert!(SettingsRegistry::validate("chat_cache.channel_retention", "{not json").is_err());
+        assert!(SettingsRegistry::validate("some_plugin.anything", "whatever").is_ok());
+    }
+
+    #[test]
+    fn test_definitions_have_valid_defaults() {
+        for def in SETTINGS {
+            if let Some(default) = def.default {
+                assert!(def.validate(default).is_ok(), "default for {} is invalid", def.key);
+            }
+        }
+    }
+
+    #[cfg(feature = "memory")]
+    #[tokio::test]
+    async fn test_set_publishes_changes_and_redacts_secrets() {
+        use crate::repositories::memory::InMemoryBotConfigRepository;
+
+        let event_bus = Arc::new(EventBus::new());
+        let mut events = event_bus.subscribe(Some(16)).await;
+        let repo = Arc::new(InMemoryBotConfigRepository::new());
+        let registry = SettingsRegistry::new(repo.clone(), event_bus);
+
+        registry.set("chat_logging.batch_size", "50").await.unwrap();
+        assert_eq!(registry.get_i64("chat_logging.batch_size"), Some(50));
+        assert!(registry.set("chat_logging.batch_size", "lots").await.is_err());
+        assert_eq!(registry.get_stored("chat_logging.batch_size").as_deref(), Some("50"));
+
+        registry.set("social.bluesky.app_password", "hunter2").await.unwrap();
+        let mut changed = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let BotEvent::ConfigChanged { key, new_value, .. } = event {
+                changed.push((key, new_value));
+            }
+        }
+        assert_eq!(changed, vec![
+            ("chat_logging.batch_size".to_string(), Some("50".to_string())),
+            ("social.bluesky.app_password".to_string(), Some(REDACTED.to_string())),
+        ]);
+
+        // Secrets only exist encrypted, including ones an older version left in plain text
+        assert_eq!(repo.get_value("social.bluesky.app_password").await.unwrap(), None);
+        assert_eq!(repo.get_secret("social.bluesky.app_password").await.unwrap().as_deref(), Some("hunter2"));
+        assert_eq!(registry.get_stored("social.bluesky.app_password").as_deref(), Some("hunter2"));
+        repo.set_value("donations.streamelements.jwt", "eyJ").await.unwrap();
+        registry.refresh().await.unwrap();
+        assert_eq!(repo.get_value("donations.streamelements.jwt").await.unwrap(), None);
+        assert_eq!(repo.get_secret("donations.streamelements.jwt").await.unwrap().as_deref(), Some("eyJ"));
+        assert_eq!(registry.get_stored("donations.streamelements.jwt").as_deref(), Some("eyJ"));
+
+        registry.delete("chat_logging.batch_size").await.unwrap();
+        assert!(!registry.is_set("chat_logging.batch_size"));
+        let default = SettingsRegistry::definition("chat_logging.batch_size").and_then(|d| d.default);
+        assert_eq!(registry.get("chat_logging.batch_size").as_deref(), default);
+    }
+}
@@ -0,0 +1,290 @@
+// File: maowbot-core/src/tasks/analysis_recompute.rs
+//
+// Recomputes every user's analysis scores from their archived chat, on demand
+// (`user analyze --all`). Users are walked in user_id order a batch at a time
+// with a short pause in between, so the bot keeps running normally; after each
+// batch the last user_id is saved as a checkpoint, and a run that was
+// cancelled or cut short by a restart can be resumed from there. Progress is
+// published on the event bus as system messages.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use parking_lot::Mutex;
+use sqlx::FromRow;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::UserAnalysis;
+use maowbot_common::traits::repository_traits::{ChatArchiveRepository, UserAnalysisRepository};
+use crate::eventbus::{BotEvent, EventBus};
+use crate::tasks::biweekly_maintenance::run_ai_scoring;
+use crate::Error;
+
+/// Users scored per batch.
+const BATCH_USERS: i64 = 100;
+/// Pause between batches, to leave the database to everyone else.
+const BATCH_PAUSE: Duration = Duration::from_millis(250);
+/// Most recent messages scored per user.
+const MAX_MESSAGES_PER_USER: i64 = 5000;
+/// Progress is published every this many percent.
+const PROGRESS_STEP_PERCENT: i64 = 10;
+
+/// One recompute run, as stored in `analysis_recompute_runs`.
+#[derive(Debug, Clone, FromRow)]
+pub struct RecomputeRun {
+    pub run_id: Uuid,
+    /// Messages in [since, until) are scored
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// running, finished, cancelled, interrupted or failed
+    pub status: String,
+    pub total_users: i32,
+    pub processed_users: i32,
+    /// Checkpoint: every user up to this id has been processed
+    pub last_user_id: Option<Uuid>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Storage for recompute runs (`analysis_recompute_runs`).
+#[async_trait]
+pub trait AnalysisRunRepository: Send + Sync {
+    /// Marks runs left "running" as interrupted; returns how many there were.
+    async fn mark_interrupted(&self) -> Result<u64, Error>;
+    /// Stores a new run in the "running" state.
+    async fn create_run(&self, since: DateTime<Utc>, until: DateTime<Utc>, total_users: i32) -> Result<RecomputeRun, Error>;
+    /// Sets the latest cancelled, interrupted or failed run running again.
+    async fn resume_latest(&self) -> Result<Option<RecomputeRun>, Error>;
+    async fn latest(&self) -> Result<Option<RecomputeRun>, Error>;
+    async fn get(&self, run_id: Uuid) -> Result<Option<RecomputeRun>, Error>;
+    async fn save_checkpoint(&self, run_id: Uuid, processed_users: i32, last_user_id: Uuid) -> Result<(), Error>;
+    /// Records the final status; `finished_at` is only set for "finished".
+    async fn finish(&self, run_id: Uuid, status: &str, error: Option<&str>) -> Result<(), Error>;
+}
+
+struct Active {
+    run_id: Uuid,
+    cancel: Arc<AtomicBool>,
+}
+
+pub struct AnalysisRecompute {
+    runs: Arc<dyn AnalysisRunRepository>,
+    chat_archive: Arc<dyn ChatArchiveRepository>,
+    analysis_repo: Arc<dyn UserAnalysisRepository>,
+    event_bus: Arc<EventBus>,
+    active: Mutex<Option<Active>>,
+}
+
+impl AnalysisRecompute {
+    pub fn new(
+        runs: Arc<dyn AnalysisRunRepository>,
+        chat_archive: Arc<dyn ChatArchiveRepository>,
+        analysis_repo: Arc<dyn UserAnalysisRepository>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self { runs, chat_archive, analysis_repo, event_bus, active: Mutex::new(None) }
+    }
+
+    /// Marks runs left "running" by a previous process as interrupted, so they can be resumed.
+    pub async fn mark_interrupted(&self) -> Result<u64, Error> {
+        self.runs.mark_interrupted().await
+    }
+
+    /// Starts a new run over the last `days` days of chat (all of it with `None`).
+    pub async fn start(self: &Arc<Self>, days: Option<i64>) -> Result<RecomputeRun, Error> {
+        self.ensure_idle()?;
+        let until = Utc::now();
+        let since = window_start(until, days)?;
+        let total = self.chat_archive.count_chatters(since, until).await?;
+        let run = self.runs.create_run(since, until, total as i32).await?;
+        self.spawn(run.clone());
+        Ok(run)
+    }
+
+    /// Continues the latest run that didn't finish from its checkpoint.
+    pub async fn resume(self: &Arc<Self>) -> Result<RecomputeRun, Error> {
+        self.ensure_idle()?;
+        let run = self.runs.resume_latest().await?
+            .ok_or_else(|| Error::NotFound("No unfinished analysis run to resume".into()))?;
+        self.spawn(run.clone());
+        Ok(run)
+    }
+
+    /// Asks the running run to stop after its current batch.
+    pub async fn cancel(&self) -> Result<RecomputeRun, Error> {
+        let run_id = {
+            let active = self.active.lock();
+            let active = active.as_ref()
+                .ok_or_else(|| Error::NotFound("No analysis run is in progress".into()))?;
+            active.cancel.store(true, Ordering::Relaxed);
+            active.run_id
+        };
+        self.runs.get(run_id).await?
+            .ok_or_else(|| Error::NotFound(format!("No analysis run {}", run_id)))
+    }
+
+    /// The most recent run, if there ever was one.
+    pub async fn latest(&self) -> Result<Option<RecomputeRun>, Error> {
+        self.runs.latest().await
+    }
+
+    fn ensure_idle(&self) -> Result<(), Error> {
+        match self.active.lock().as_ref() {
+            Some(active) => Err(Error::ValidationError(format!("Analysis run {} is already in progress", active.run_id))),
+            None => Ok(()),
+        }
+    }
+
+    fn spawn(self: &Arc<Self>, run: RecomputeRun) {
+        let cancel = Arc::new(AtomicBool::new(false));
+        *self.active.lock() = Some(Active { run_id: run.run_id, cancel: cancel.clone() });
+        let this = self.clone();
+        tokio::spawn(async move {
+            let run_id = run.run_id;
+            let (status, error) = match this.process(run, &cancel).await {
+                Ok(true) => ("finished", None),
+                Ok(false) => ("cancelled", None),
+                // Left "running" in the table; mark_interrupted picks it up on the next start
+                Err(_) if this.event_bus.is_shutdown() => {
+                    *this.active.lock() = None;
+                    return;
+                }
+                Err(e) => {
+                    error!("Analysis run {} failed: {:?}", run_id, e);
+                    ("failed", Some(e.to_string()))
+                }
+            };
+            if let Err(e) = this.finish(run_id, status, error.as_deref()).await {
+                warn!("Could not record the end of analysis run {}: {:?}", run_id, e);
+            }
+            *this.active.lock() = None;
+        });
+    }
+
+    /// Works through the remaining users; returns false if cancelled.
+    async fn process(&self, run: RecomputeRun, cancel: &AtomicBool) -> Result<bool, Error> {
+        self.progress(&run, run.processed_users as i64, "started").await;
+        let mut checkpoint = run.last_user_id;
+        let mut processed = run.processed_users as i64;
+        let mut next_report = next_report_percent(percent_done(processed, run.total_users));
+
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(false);
+            }
+            if self.event_bus.is_shutdown() {
+                return Err(Error::Internal("Server shutting down".into()));
+            }
+
+            let users = self.chat_archive.chatters(run.since, run.until, checkpoint, BATCH_USERS).await?;
+            let Some(&last) = users.last() else { return Ok(true) };
+
+            for user_id in &users {
+                self.rescore(*user_id, run.since, run.until).await?;
+            }
+            processed += users.len() as i64;
+            checkpoint = Some(last);
+            self.runs.save_checkpoint(run.run_id, processed as i32, last).await?;
+
+            let percent = percent_done(processed, run.total_users);
+            if percent >= next_report {
+                self.progress(&run, processed, "in progress").await;
+                next_report = next_report_percent(percent);
+            }
+            tokio::time::sleep(BATCH_PAUSE).await;
+        }
+    }
+
+    /// Replaces one user's scores with ones computed from their messages in the window.
+    async fn rescore(&self, user_id: Uuid, since: DateTime<Utc>, until: DateTime<Utc>) -> Result<(), Error> {
+        let messages = self.chat_archive
+            .messages_for_user_between(user_id, since, until, Some(MAX_MESSAGES_PER_USER))
+            .await?;
+        let (spam, intel, quality, horni, _summary) = run_ai_scoring(&messages).await;
+
+        match self.analysis_repo.get_analysis(user_id).await? {
+            Some(mut analysis) => {
+                analysis.spam_score = spam;
+                analysis.intelligibility_score = intel;
+                analysis.quality_score = quality;
+                analysis.horni_score = horni;
+                self.analysis_repo.update_analysis(&analysis).await
+            }
+            None => {
+                let mut analysis = UserAnalysis::new(user_id);
+                analysis.spam_score = spam;
+                analysis.intelligibility_score = intel;
+                analysis.quality_score = quality;
+                analysis.horni_score = horni;
+                self.analysis_repo.create_analysis(&analysis).await
+            }
+        }
+    }
+
+    async fn finish(&self, run_id: Uuid, status: &str, error: Option<&str>) -> Result<(), Error> {
+        self.runs.finish(run_id, status, error).await?;
+        let text = format!("User analysis run {}: {}", short_id(run_id), status);
+        info!("{}", text);
+        self.event_bus.publish(BotEvent::SystemMessage(text)).await;
+        Ok(())
+    }
+
+    async fn progress(&self, run: &RecomputeRun, processed: i64, what: &str) {
+        let text = format!(
+            "User analysis run {} {}: {}/{} users",
+            short_id(run.run_id), what, processed, run.total_users
+        );
+        info!("{}", text);
+        self.event_bus.publish(BotEvent::SystemMessage(text)).await;
+    }
+}
+
+/// Start of the scored window: `days` back from `until`, or all of history.
+fn window_start(until: DateTime<Utc>, days: Option<i64>) -> Result<DateTime<Utc>, Error> {
+    match days {
+        Some(d) if d > 0 => Ok(until - chrono::Duration::days(d)),
+        Some(_) => Err(Error::ValidationError("days must be positive".into())),
+        None => Ok(Utc.timestamp_opt(0, 0).unwrap()),
+    }
+}
+
+fn percent_done(processed: i64, total_users: i32) -> i64 {
+    processed * 100 / (total_users.max(1) as i64)
+}
+
+/// The next progress step above `percent`.
+fn next_report_percent(percent: i64) -> i64 {
+    (percent / PROGRESS_STEP_PERCENT + 1) * PROGRESS_STEP_PERCENT
+}
+
+fn short_id(id: Uuid) -> String {
+    id.to_string().chars().take(8).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_covers_the_requested_days_or_everything() {
+        let until = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+        assert_eq!(window_start(until, Some(7)).unwrap(), Utc.with_ymd_and_hms(2026, 10, 9, 0, 0, 0).unwrap());
+        assert_eq!(window_start(until, None).unwrap().timestamp(), 0);
+        assert!(matches!(window_start(until, Some(0)), Err(Error::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_progress_is_reported_in_steps_and_resumes_past_the_last_one() {
+        assert_eq!(next_report_percent(percent_done(0, 250)), 10);
+        assert_eq!(next_report_percent(percent_done(100, 250)), 50);
+        assert_eq!(next_report_percent(percent_done(99, 250)), 40);
+        // A run with no users still finishes its reporting
+        assert_eq!(percent_done(0, 0), 0);
+        assert_eq!(percent_done(250, 250), 100);
+    }
+}
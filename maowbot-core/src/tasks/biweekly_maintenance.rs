@@ -2,22 +2,28 @@
 
 use std::sync::Arc;
 use chrono::{DateTime, Utc, NaiveDate, Datelike};
-use sqlx::{Pool, Postgres, Row};
+use sqlx::{Pool, Postgres};
 use tracing::{info, error, debug};
 use std::time::Duration;
 use sqlx::error::BoxDynError;
 use uuid::Uuid;
 use maowbot_common::models::UserAnalysis;
-use crate::db::Database;
-use crate::repositories::postgres::user_analysis::{PostgresUserAnalysisRepository, UserAnalysisRepository};
-use crate::repositories::postgres::analytics::ChatMessage;
+use maowbot_common::traits::repository_traits::{ChatArchiveRepository, UserAnalysisRepository};
+use crate::db::{Database, DbConnection};
+use maowbot_common::models::analytics::ChatMessage;
 use crate::Error;
 use crate::eventbus::EventBus;
+use crate::tasks::retention::RetentionEngine;
+
+/// Users summarized per chat archive query.
+const SUMMARY_BATCH_USERS: i64 = 500;
 
 pub fn spawn_biweekly_maintenance_task(
-    db: Database,
-    user_analysis_repo: PostgresUserAnalysisRepository,
+    db: DbConnection,
+    user_analysis_repo: Arc<dyn UserAnalysisRepository>,
+    chat_archive: Arc<dyn ChatArchiveRepository>,
     event_bus: Arc<EventBus>,  // <--- pass in
+    retention: Arc<RetentionEngine>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(14 * 24 * 3600));
@@ -27,9 +33,12 @@ pub fn spawn_biweekly_maintenance_task(
         loop {
             tokio::select! {
                 _ = interval.tick() => {
-                    if let Err(e) = run_biweekly_maintenance(&db, &user_analysis_repo).await {
+                    if let Err(e) = run_biweekly_maintenance(&db, user_analysis_repo.as_ref(), chat_archive.as_ref()).await {
                         error!("Biweekly maintenance failed: {:?}", e);
                     }
+                    if let Err(e) = retention.run(false).await {
+                        error!("Retention failed: {:?}", e);
+                    }
                 },
                 Ok(_) = shutdown_rx.changed() => {
                     if *shutdown_rx.borrow() {
@@ -45,22 +54,26 @@ pub fn spawn_biweekly_maintenance_task(
 }
 
 /// Runs biweekly maintenance: creates partitions for current + next month, then user analysis.
+/// Only Postgres has partitions; the other backends go straight to the analysis.
 pub async fn run_biweekly_maintenance(
-    db: &Database,
-    user_analysis_repo: &PostgresUserAnalysisRepository,
+    db: &DbConnection,
+    user_analysis_repo: &dyn UserAnalysisRepository,
+    chat_archive: &dyn ChatArchiveRepository,
 ) -> Result<(), Error> {
     info!("Starting biweekly maintenance tasks...");
 
-    // 1) Create partitions for all partitioned tables
-    run_partition_maintenance(db).await?;
-    info!("Partition creation done...");
+    if let Some(db) = db.postgres() {
+        // 1) Create partitions for all partitioned tables
+        run_partition_maintenance(db).await?;
+        info!("Partition creation done...");
 
-    // 2) Drop old partitions based on retention policies
-    run_partition_cleanup(db).await?;
-    info!("Partition cleanup done...");
+        // 2) Drop old partitions based on retention policies
+        run_partition_cleanup(db).await?;
+        info!("Partition cleanup done...");
+    }
 
     // 3) User analysis
-    run_analysis(db, user_analysis_repo).await?;
+    run_analysis(user_analysis_repo, chat_archive).await?;
     info!("Analysis done...");
 
     info!("Biweekly maintenance is complete.");
@@ -132,7 +145,7 @@ pub async fn run_partition_maintenance(db: &Database) -> Result<(), Error> {
 async fn create_month_partition_if_needed(
     pool: &Pool<Postgres>,
     table_name: &str,
-    time_column: &str,
+    _time_column: &str,
     first_day: NaiveDate
 ) -> Result<(), Error> {
     let year = first_day.year();
@@ -203,20 +216,38 @@ pub async fn run_partition_cleanup(db: &Database) -> Result<(), Error> {
     
     // Get default retention days from config
     let default_retention: i64 = sqlx::query_scalar(
-        "SELECT COALESCE(config_value::bigint, 30) FROM bot_config WHERE config_key = 'chat_logging.default_retention_days'"
+        "SELECT COALESCE(config_value::bigint, 30) FROM bot_config WHERE workspace_id = '00000000-0000-0000-0000-000000000000' AND config_key = 'chat_logging.default_retention_days'"
     )
     .fetch_optional(pool)
     .await?
     .unwrap_or(30);
     
+    // Whole chat partitions can only go once no channel keeps them any longer
+    let longest_chat_retention: i64 = sqlx::query_scalar(
+        "SELECT GREATEST($1, COALESCE(MAX(retention_days), 0))::bigint FROM chat_logging_config"
+    )
+    .bind(default_retention)
+    .fetch_one(pool)
+    .await?;
+
+    // 0 keeps command usage forever (see tasks::retention)
+    let command_usage_retention: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(config_value::bigint, 30) FROM bot_config WHERE workspace_id = '00000000-0000-0000-0000-000000000000' AND config_key = 'retention.command_usage_days'"
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or(30);
+
     // Get all partitioned tables and their retention policies
-    let retention_configs: Vec<(String, i64)> = vec![
-        ("chat_messages".to_string(), default_retention),
+    let mut retention_configs: Vec<(String, i64)> = vec![
+        ("chat_messages".to_string(), longest_chat_retention),
         ("analytics_events".to_string(), 90), // Keep analytics for 3 months
-        ("command_usage".to_string(), 30),
         ("redeem_usage".to_string(), 30),
         ("pipeline_execution_log".to_string(), 7), // Only keep pipeline logs for 7 days
     ];
+    if command_usage_retention > 0 {
+        retention_configs.push(("command_usage".to_string(), command_usage_retention));
+    }
     
     let now = Utc::now();
     
@@ -240,14 +271,14 @@ pub async fn run_partition_cleanup(db: &Database) -> Result<(), Error> {
         
         for partition in old_partitions {
             // Extract year and month from partition name (format: tablename_YYYYMM)
-            if let Some(date_part) = partition.split('_').last() {
+            if let Some(date_part) = partition.split('_').next_back() {
                 if date_part.len() == 6 {
                     if let (Ok(year), Ok(month)) = (
                         date_part[0..4].parse::<i32>(),
                         date_part[4..6].parse::<u32>()
                     ) {
                         let partition_start = NaiveDate::from_ymd_opt(year, month, 1);
-                        if let Some(partition_start_date) = partition_start {
+                        if let Some(_partition_start_date) = partition_start {
                             // Calculate the last day of the partition's month
                             let next_month = if month == 12 {
                                 NaiveDate::from_ymd_opt(year + 1, 1, 1)
@@ -285,130 +316,90 @@ pub async fn run_partition_cleanup(db: &Database) -> Result<(), Error> {
             }
         }
     }
-    
+
+    // Rows inside the partitions that are kept are left to tasks::retention
     Ok(())
 }
 
 /// Runs user analysis (aggregates last 30 days of chat_messages).
 pub async fn run_analysis(
-    db: &Database,
-    user_analysis_repo: &PostgresUserAnalysisRepository,
+    user_analysis_repo: &dyn UserAnalysisRepository,
+    chat_archive: &dyn ChatArchiveRepository,
 ) -> Result<(), Error> {
     info!("Running user analysis...");
     let start_ts = Utc::now() - chrono::Duration::days(30);
     let end_ts   = Utc::now();
-    generate_user_summaries(db, user_analysis_repo, start_ts, end_ts).await
+    generate_user_summaries(user_analysis_repo, chat_archive, start_ts, end_ts).await
 }
 
 /// Gathers each user's messages in [start_ts, end_ts), runs AI scoring,
 /// and updates `user_analysis` + `user_analysis_history`.
 async fn generate_user_summaries(
-    db: &Database,
-    user_analysis_repo: &PostgresUserAnalysisRepository,
+    user_analysis_repo: &dyn UserAnalysisRepository,
+    chat_archive: &dyn ChatArchiveRepository,
     start_ts: DateTime<Utc>,
     end_ts: DateTime<Utc>,
 ) -> Result<(), Error> {
     info!("Generating user summaries from {} to {}", start_ts, end_ts);
 
-    // 1) Distinct user_id in that time range
-    let user_rows = sqlx::query(
-        r#"
-        SELECT DISTINCT user_id
-        FROM chat_messages
-        WHERE timestamp >= $1 AND timestamp < $2
-        "#,
-    )
-        .bind(start_ts)
-        .bind(end_ts)
-        .fetch_all(db.pool())
-        .await?;
-
-    // 2) For each user, gather messages & compute analysis
-    for row in user_rows {
-        let user_id: Uuid = row.try_get("user_id")?;
-
-        let messages = sqlx::query_as::<_, ChatMessage>(
-            r#"
-            SELECT message_id, platform, channel, user_id, message_text, timestamp, metadata
-            FROM chat_messages
-            WHERE user_id = $1 AND timestamp >= $2 AND timestamp < $3
-            "#,
-        )
-            .bind(&user_id)
-            .bind(start_ts)
-            .bind(end_ts)
-            .fetch_all(db.pool())
-            .await?;
-
-        let (spam, intel, quality, horni, summary) = run_ai_scoring(&messages).await;
-
-        // Insert a user_analysis_history entry
-        let hist_id = Uuid::new_v4();
-        let year_month = format!("{}-{:02}", Utc::now().year(), Utc::now().month());
-        sqlx::query(
-            r#"
-            INSERT INTO user_analysis_history (
-                user_analysis_history_id,
-                user_id,
-                year_month,
-                spam_score,
-                intelligibility_score,
-                quality_score,
-                horni_score,
-                ai_notes,
-                created_at
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            "#,
-        )
-            .bind(hist_id)
-            .bind(&user_id)
-            .bind(&year_month)
-            .bind(spam)
-            .bind(intel)
-            .bind(quality)
-            .bind(horni)
-            .bind(&summary)
-            .bind(Utc::now())
-            .execute(db.pool())
-            .await?;
-
-        // Update or create user_analysis
-        if let Some(mut analysis) = user_analysis_repo.get_analysis(user_id).await? {
-            // Simple weighted approach
-            analysis.spam_score = 0.7 * analysis.spam_score + 0.3 * spam;
-            analysis.intelligibility_score = 0.7 * analysis.intelligibility_score + 0.3 * intel;
-            analysis.quality_score = 0.7 * analysis.quality_score + 0.3 * quality;
-            analysis.horni_score = 0.7 * analysis.horni_score + 0.3 * horni;
-
-            let old_notes = analysis.ai_notes.clone().unwrap_or_default();
-            let appended_notes = format!("{}\n\n=== {} summary ===\n{}", old_notes, year_month, summary);
-            analysis.ai_notes = Some(appended_notes);
-
-            user_analysis_repo.update_analysis(&analysis).await?;
-        } else {
-            // If there's no existing analysis, create a brand-new one:
-            let new_one = UserAnalysis {
-                user_analysis_id: Uuid::new_v4(),
-                user_id: user_id.clone(),
-                spam_score: spam,
-                intelligibility_score: intel,
-                quality_score: quality,
-                horni_score: horni,
-                ai_notes: Some(summary),
-                moderator_notes: None,
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-            };
-            user_analysis_repo.create_analysis(&new_one).await?;
+    let mut after = None;
+    loop {
+        // 1) The next batch of users who chatted in that time range
+        let users = chat_archive.chatters(start_ts, end_ts, after, SUMMARY_BATCH_USERS).await?;
+        let Some(&last) = users.last() else { break };
+        after = Some(last);
+
+        // 2) For each user, gather messages & compute analysis
+        for user_id in users {
+            let messages = chat_archive.messages_for_user_between(user_id, start_ts, end_ts, None).await?;
+            summarize_user(user_analysis_repo, user_id, &messages).await?;
         }
     }
 
     Ok(())
 }
 
+/// Scores one user's messages, records the month's history entry and folds
+/// the scores into their running analysis.
+async fn summarize_user(
+    user_analysis_repo: &dyn UserAnalysisRepository,
+    user_id: Uuid,
+    messages: &[ChatMessage],
+) -> Result<(), Error> {
+    let (spam, intel, quality, horni, summary) = run_ai_scoring(messages).await;
+
+    // Insert a user_analysis_history entry
+    let year_month = format!("{}-{:02}", Utc::now().year(), Utc::now().month());
+    let mut month = UserAnalysis::new(user_id);
+    month.spam_score = spam;
+    month.intelligibility_score = intel;
+    month.quality_score = quality;
+    month.horni_score = horni;
+    month.ai_notes = Some(summary.clone());
+    user_analysis_repo.insert_history(&year_month, &month).await?;
+
+    // Update or create user_analysis
+    if let Some(mut analysis) = user_analysis_repo.get_analysis(user_id).await? {
+        // Simple weighted approach
+        analysis.spam_score = 0.7 * analysis.spam_score + 0.3 * spam;
+        analysis.intelligibility_score = 0.7 * analysis.intelligibility_score + 0.3 * intel;
+        analysis.quality_score = 0.7 * analysis.quality_score + 0.3 * quality;
+        analysis.horni_score = 0.7 * analysis.horni_score + 0.3 * horni;
+
+        let old_notes = analysis.ai_notes.clone().unwrap_or_default();
+        let appended_notes = format!("{}\n\n=== {} summary ===\n{}", old_notes, year_month, summary);
+        analysis.ai_notes = Some(appended_notes);
+
+        user_analysis_repo.update_analysis(&analysis).await?;
+    } else {
+        // If there's no existing analysis, create a brand-new one
+        user_analysis_repo.create_analysis(&month).await?;
+    }
+    Ok(())
+}
+
 /// Dummy AI scoring function just for illustration.
-async fn run_ai_scoring(messages: &[ChatMessage]) -> (f32, f32, f32, f32, String) {
+pub(crate) async fn run_ai_scoring(messages: &[ChatMessage]) -> (f32, f32, f32, f32, String) {
     let count = messages.len() as f32;
     // Example logic
     let spam = 0.1 * count.min(5.0);
@@ -1,52 +1,226 @@
-use chrono::{Duration};
-use tracing::{info, error};
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::Mutex;
+use tracing::{info, error, warn};
+use uuid::Uuid;
+use maowbot_common::models::platform::PlatformCredential;
 use maowbot_common::traits::repository_traits::CredentialsRepository;
 use crate::auth::manager::AuthManager;
+use crate::eventbus::{BotEvent, EventBus};
 use crate::Error;
-use crate::repositories::postgres::credentials::{PostgresCredentialsRepository};
 
+/// How often the scheduler looks for expiring credentials, and how far ahead.
+#[derive(Debug, Clone)]
+pub struct CredentialRefreshSchedule {
+    pub check_interval: std::time::Duration,
+    /// Credentials expiring within this window are renewed.
+    pub refresh_window: Duration,
+}
+
+impl Default for CredentialRefreshSchedule {
+    fn default() -> Self {
+        Self {
+            check_interval: std::time::Duration::from_secs(10 * 60),
+            refresh_window: Duration::minutes(60),
+        }
+    }
+}
+
+/// A credential that could not be renewed.
+#[derive(Debug, Clone)]
+pub struct RefreshFailure {
+    pub credential: PlatformCredential,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RefreshSummary {
+    pub refreshed: usize,
+    pub failed: Vec<RefreshFailure>,
+    /// Expiring credentials skipped because they failed recently.
+    pub deferred: usize,
+}
+
+/// Longest wait between attempts for a credential that keeps failing.
+const MAX_BACKOFF_MINUTES: i64 = 6 * 60;
+
+/// Keeps a failing credential from being retried on every check: it waits
+/// `base` after the first failure, doubling with each further one up to
+/// `MAX_BACKOFF_MINUTES`. A success clears it.
+#[derive(Debug, Default)]
+pub struct RefreshBackoff {
+    /// credential_id => (consecutive failures, earliest next attempt)
+    failures: HashMap<Uuid, (u32, DateTime<Utc>)>,
+}
+
+impl RefreshBackoff {
+    pub fn is_due(&self, credential_id: Uuid, now: DateTime<Utc>) -> bool {
+        self.failures.get(&credential_id).is_none_or(|(_, next)| *next <= now)
+    }
+
+    /// Records a failure and returns when the credential may be tried again.
+    pub fn record_failure(&mut self, credential_id: Uuid, now: DateTime<Utc>, base: Duration) -> DateTime<Utc> {
+        let failures = self.failures.get(&credential_id).map_or(0, |(n, _)| *n) + 1;
+        let delay = (base * 2i32.saturating_pow(failures.min(16) - 1)).min(Duration::minutes(MAX_BACKOFF_MINUTES));
+        let next = now + delay;
+        self.failures.insert(credential_id, (failures, next));
+        next
+    }
 
-/// Checks for credentials that will expire within `within_minutes` from now.
-/// For each such credential, calls `AuthManager::refresh_platform_credentials`.
+    pub fn record_success(&mut self, credential_id: Uuid) {
+        self.failures.remove(&credential_id);
+    }
+}
+
+/// Renews one credential; `AuthManager` in the server, a stub in tests.
+#[async_trait]
+pub trait CredentialRefresher: Send {
+    async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error>;
+}
+
+#[async_trait]
+impl CredentialRefresher for AuthManager {
+    async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
+        self.refresh_platform_credentials(&credential.platform, &credential.user_id).await
+    }
+}
+
+/// Checks for credentials that will expire within `within_minutes` from now
+/// and renews each one that isn't backing off from a recent failure; the
+/// refresher stores the renewed token.
 ///
-/// Returns Ok(()) even if some credentials fail to refresh (logs errors).
+/// Credentials without a refresh token can't be renewed and are reported as
+/// failures. Returns Ok even if some credentials fail to refresh.
 pub async fn refresh_expiring_tokens(
-    creds_repo: &PostgresCredentialsRepository,
-    auth_manager: &mut AuthManager,
+    creds_repo: &dyn CredentialsRepository,
+    refresher: &mut dyn CredentialRefresher,
+    backoff: &mut RefreshBackoff,
+    retry_after: Duration,
     within_minutes: i64,
-) -> Result<(), Error> {
+) -> Result<RefreshSummary, Error> {
     let duration = Duration::minutes(within_minutes);
     let expiring = creds_repo.get_expiring_credentials(duration).await?;
+    let mut summary = RefreshSummary::default();
 
     if expiring.is_empty() {
         info!("No credentials expiring in the next {} minutes.", within_minutes);
-        return Ok(());
+        return Ok(summary);
     }
 
     info!("Found {} credential(s) expiring soon; attempting to refresh...", expiring.len());
 
-    // For each credential, attempt to refresh
+    let now = Utc::now();
     for cred in expiring {
-        let platform = cred.platform.clone();
-        let user_id = cred.user_id;
+        if !backoff.is_due(cred.credential_id, now) {
+            summary.deferred += 1;
+            continue;
+        }
+        if cred.refresh_token.is_none() {
+            warn!(
+                "Credential for platform={:?}, user={} expires at {:?} and has no refresh token",
+                cred.platform, cred.user_name, cred.expires_at
+            );
+            backoff.record_failure(cred.credential_id, now, retry_after);
+            summary.failed.push(RefreshFailure {
+                credential: cred,
+                error: "No refresh token; re-authenticate this account".to_string(),
+            });
+            continue;
+        }
 
-        match auth_manager.refresh_platform_credentials(&platform, &user_id).await {
+        match refresher.refresh(&cred).await {
             Ok(new_cred) => {
                 info!(
                     "Successfully refreshed credential for platform={:?}, user_id={}",
                     new_cred.platform, new_cred.user_id
                 );
+                backoff.record_success(cred.credential_id);
+                summary.refreshed += 1;
             }
             Err(e) => {
+                let next = backoff.record_failure(cred.credential_id, now, retry_after);
                 error!(
-                    "Failed to refresh credential for platform={:?}, user_id={}: {:?}",
-                    platform, user_id, e
+                    "Failed to refresh credential for platform={:?}, user_id={}: {:?} (next attempt after {})",
+                    cred.platform, cred.user_id, e, next
                 );
+                summary.failed.push(RefreshFailure { credential: cred, error: e.to_string() });
             }
         }
     }
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Spawns the background task that renews credentials before they expire.
+///
+/// Every failure is published as `BotEvent::CredentialRefreshFailed`, once per
+/// credential and expiry time, so a notifier pipeline isn't re-triggered on
+/// every check while the token stays broken.
+pub fn spawn_credential_refresh_task(
+    creds_repo: Arc<dyn CredentialsRepository + Send + Sync>,
+    auth_manager: Arc<Mutex<AuthManager>>,
+    event_bus: Arc<EventBus>,
+    schedule: CredentialRefreshSchedule,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(schedule.check_interval);
+        let mut shutdown_rx = event_bus.shutdown_rx.clone();
+        // credential_id => expires_at we already alerted about
+        let mut alerted: HashMap<Uuid, Option<DateTime<Utc>>> = HashMap::new();
+        let mut backoff = RefreshBackoff::default();
+        let retry_after = Duration::from_std(schedule.check_interval).unwrap_or(Duration::minutes(10));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let result = {
+                        let mut auth_lock = auth_manager.lock().await;
+                        refresh_expiring_tokens(
+                            creds_repo.as_ref(),
+                            &mut *auth_lock,
+                            &mut backoff,
+                            retry_after,
+                            schedule.refresh_window.num_minutes(),
+                        ).await
+                    };
+
+                    match result {
+                        Ok(summary) => {
+                            if summary.refreshed > 0 || !summary.failed.is_empty() {
+                                info!(
+                                    "Periodic token refresh completed: {} refreshed, {} failed",
+                                    summary.refreshed, summary.failed.len()
+                                );
+                            }
+                            for failure in summary.failed {
+                                let cred = &failure.credential;
+                                if alerted.get(&cred.credential_id) == Some(&cred.expires_at) {
+                                    continue;
+                                }
+                                alerted.insert(cred.credential_id, cred.expires_at);
+                                event_bus.publish(BotEvent::CredentialRefreshFailed {
+                                    credential_id: cred.credential_id,
+                                    platform: cred.platform.to_string(),
+                                    user_name: cred.user_name.clone(),
+                                    expires_at: cred.expires_at,
+                                    error: failure.error,
+                                }).await;
+                            }
+                        }
+                        Err(e) => error!("Periodic token refresh failed: {:?}", e),
+                    }
+                },
+                Ok(_) = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        info!("Credential refresh task: shutting down cleanly.");
+                        break;
+                    }
+                }
+            }
+        }
+    })
 }
 
 /// Refreshes **all** credentials in the database that have a valid `refresh_token`.
@@ -55,7 +229,7 @@ pub async fn refresh_expiring_tokens(
 ///
 /// This also covers the scenario where we simply want to refresh everything at startup.
 pub async fn refresh_all_refreshable_credentials(
-    creds_repo: &PostgresCredentialsRepository,
+    creds_repo: &dyn CredentialsRepository,
     auth_manager: &mut AuthManager,
 ) -> Result<(), Error> {
     let all_creds = creds_repo.get_all_credentials().await?;
@@ -101,4 +275,103 @@ pub async fn refresh_all_refreshable_credentials(
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap_and_clears_on_success() {
+        let mut backoff = RefreshBackoff::default();
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        let base = Duration::minutes(10);
+
+        assert!(backoff.is_due(id, now));
+        assert_eq!(backoff.record_failure(id, now, base), now + Duration::minutes(10));
+        assert!(!backoff.is_due(id, now + Duration::minutes(9)));
+        assert!(backoff.is_due(id, now + Duration::minutes(10)));
+        assert_eq!(backoff.record_failure(id, now, base), now + Duration::minutes(20));
+        assert_eq!(backoff.record_failure(id, now, base), now + Duration::minutes(40));
+        for _ in 0..20 {
+            backoff.record_failure(id, now, base);
+        }
+        assert_eq!(backoff.record_failure(id, now, base), now + Duration::minutes(MAX_BACKOFF_MINUTES));
+
+        backoff.record_success(id);
+        assert!(backoff.is_due(id, now));
+    }
+
+    #[cfg(feature = "memory")]
+    mod with_repository {
+        use super::*;
+        use maowbot_common::models::credential::CredentialType;
+        use maowbot_common::models::platform::Platform;
+        use crate::repositories::memory::credentials::InMemoryCredentialsRepository;
+
+        /// Renews every credential except those for `broken_user`.
+        struct StubRefresher {
+            broken_user: String,
+            calls: Vec<String>,
+        }
+
+        #[async_trait]
+        impl CredentialRefresher for StubRefresher {
+            async fn refresh(&mut self, credential: &PlatformCredential) -> Result<PlatformCredential, Error> {
+                self.calls.push(credential.user_name.clone());
+                if credential.user_name == self.broken_user {
+                    return Err(Error::Auth("refresh token revoked".into()));
+                }
+                Ok(credential.clone())
+            }
+        }
+
+        fn credential(user_name: &str, expires_in: Duration) -> PlatformCredential {
+            let now = Utc::now();
+            PlatformCredential {
+                credential_id: Uuid::new_v4(),
+                platform: Platform::Twitch,
+                platform_id: None,
+                credential_type: CredentialType::OAuth2,
+                user_id: Uuid::new_v4(),
+                user_name: user_name.to_string(),
+                primary_token: "token".to_string(),
+                refresh_token: Some("refresh".to_string()),
+                additional_data: None,
+                expires_at: Some(now + expires_in),
+                created_at: now,
+                updated_at: now,
+                is_bot: false,
+                is_teammate: false,
+                is_broadcaster: false,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_refreshes_due_credentials_and_backs_off_failures() {
+            let repo = InMemoryCredentialsRepository::new();
+            repo.store_credentials(&credential("due", Duration::minutes(5))).await.unwrap();
+            repo.store_credentials(&credential("broken", Duration::minutes(5))).await.unwrap();
+            repo.store_credentials(&credential("later", Duration::days(2))).await.unwrap();
+
+            let mut refresher = StubRefresher { broken_user: "broken".into(), calls: Vec::new() };
+            let mut backoff = RefreshBackoff::default();
+            let summary = refresh_expiring_tokens(&repo, &mut refresher, &mut backoff, Duration::minutes(10), 60)
+                .await.unwrap();
+            assert_eq!(summary.refreshed, 1);
+            assert_eq!(summary.failed.len(), 1);
+            assert_eq!(summary.failed[0].credential.user_name, "broken");
+            refresher.calls.sort();
+            assert_eq!(refresher.calls, vec!["broken", "due"]);
+
+            // The failed credential waits out its backoff; the other is tried again
+            refresher.calls.clear();
+            let summary = refresh_expiring_tokens(&repo, &mut refresher, &mut backoff, Duration::minutes(10), 60)
+                .await.unwrap();
+            assert_eq!(summary.deferred, 1);
+            assert!(summary.failed.is_empty());
+            assert_eq!(refresher.calls, vec!["due"]);
+        }
+    }
+}
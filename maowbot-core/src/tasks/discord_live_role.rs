@@ -1,17 +1,16 @@
 use std::sync::Arc;
 use tokio::time::{interval, Duration};
 use tracing::{debug, error, info, warn, trace};
-use std::collections::HashSet;
 
 use twilight_model::{
     gateway::presence::ActivityType,
-    id::{marker::{GuildMarker, RoleMarker, UserMarker}, Id},
+    id::{marker::{GuildMarker, RoleMarker}, Id},
 };
 
 use crate::Error;
 use crate::platforms::discord::DiscordPlatform;
 use crate::platforms::manager::PlatformManager;
-use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_common::models::platform::Platform;
 use maowbot_common::traits::repository_traits::DiscordRepository;
 
 /// Checks streaming status for all users in guilds with live roles configured
@@ -108,7 +107,7 @@ pub async fn check_all_streaming_status(
                     match http.guild_member(guild_id, user_id).await {
                         Ok(member_response) => {
                             match member_response.model().await {
-                                Ok(member_detail) => {
+                                Ok(_member_detail) => {
                                     trace!("Checking streaming status for user with role: {}", user_id);
                                     
                                     // Member detail doesn't directly have presence data
@@ -337,8 +336,8 @@ pub async fn verify_streaming_status_at_startup(
                 Ok(live_roles) => {
                     info!("Found {} configured live roles", live_roles.len());
                     
-                    let mut total_presences = 0;
-                    let mut streaming_count = 0;
+                    let _total_presences = 0;
+                    let _streaming_count = 0;
                     
                     // Check each guild with a live role
                     for live_role in &live_roles {
@@ -444,11 +443,9 @@ async fn find_active_discord_account(
         if platform == "discord" && handle.discord_instance.is_some() {
             // Get the username for this user_id
             let creds_repo = &platform_manager.credentials_repo;
-            if let Ok(cred) = creds_repo.get_credentials(&Platform::Discord, uuid::Uuid::parse_str(user_id).unwrap_or_default()).await {
-                if let Some(credential) = cred {
-                    active_accounts.push(credential.user_name);
-                    continue;
-                }
+            if let Ok(Some(credential)) = creds_repo.get_credentials(&Platform::Discord, uuid::Uuid::parse_str(user_id).unwrap_or_default()).await {
+                active_accounts.push(credential.user_name);
+                continue;
             }
             
             // Fallback: just use the user_id as the account name if we have a discord instance
@@ -3,4 +3,6 @@ pub mod credential_refresh;
 pub mod biweekly_maintenance;
 pub mod autostart;
 pub mod redeem_sync;
-pub mod discord_live_role;
\ No newline at end of file
+pub mod discord_live_role;
+pub mod analysis_recompute;
+pub mod retention;
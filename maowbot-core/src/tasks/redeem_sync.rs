@@ -8,27 +8,14 @@ use crate::services::user_service::UserService;
 use crate::platforms::twitch::client::TwitchHelixClient;
 use crate::platforms::twitch::requests::channel_points::{CustomRewardBody, CustomReward};
 use crate::repositories::postgres::bot_config::BotConfigRepository;
+use crate::services::twitch::broadcaster_helix;
 use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use maowbot_common::models::platform::Platform;
 use maowbot_common::models::Redeem;
 use maowbot_common::traits::repository_traits::{CredentialsRepository, RedeemRepository};
 
-fn find_reward_id_by_title_ignorecase(list: &[CustomReward], wanted_title: &str) -> Option<String> {
-    let target = wanted_title.to_lowercase();
-    list.iter().find_map(|r| {
-        if r.title.to_lowercase() == target {
-            Some(r.id.clone())
-        } else {
-            None
-        }
-    })
-}
-
-fn is_in_list(list: &[CustomReward], reward_id: &str) -> bool {
-    list.iter().any(|r| r.id == reward_id)
-}
-
 /// The main function to sync local DB redeems to Twitch Helix **and** import
 /// any previously unknown Twitch rewards into our DB.
 ///
@@ -148,6 +135,7 @@ pub async fn sync_channel_redeems(
                 active_credential_id: None,
                 is_input_required: helix_rd.is_user_input_required,
                 redeem_prompt_text: None,
+                requires_approval: false,
             };
 
             if let Err(e) = redeem_service.redeem_repo.create_redeem(&new_redeem).await {
@@ -201,81 +189,494 @@ async fn sync_one_redeem_via_helix(
     // 2) see if Helix already has it by reward_id
     let maybe_helix_rd = all_rewards.iter().find(|r| r.id == rd.reward_id);
 
-    if maybe_helix_rd.is_none() {
-        // Possibly Helix was missing that reward. Let’s see if we can unify by name
-        // if the reward_id is empty. (We do this only if reward_id was never set.)
-        if rd.reward_id.trim().is_empty() {
-            // Attempt to create
-            let body = CustomRewardBody {
-                title: Some(rd.reward_name.clone()),
-                cost: Some(rd.cost as u64),
-                is_enabled: Some(rd.is_active),
-                is_user_input_required: Some(rd.is_input_required),
-                ..Default::default()
-            };
-            match client.create_custom_reward(broadcaster_id, &body).await {
-                Ok(created) => {
-                    // update DB to store the new Helix ID
-                    let mut updated_rd = rd.clone();
-                    updated_rd.reward_id = created.id;
-                    updated_rd.updated_at = Utc::now();
-                    redeem_service.redeem_repo.update_redeem(&updated_rd).await?;
-                    info!("Created new Helix reward for '{}' => new ID={}", rd.reward_name, updated_rd.reward_id);
+    match maybe_helix_rd {
+        None => {
+            // Possibly Helix was missing that reward. Let’s see if we can unify by name
+            // if the reward_id is empty. (We do this only if reward_id was never set.)
+            if rd.reward_id.trim().is_empty() {
+                // Attempt to create
+                let body = CustomRewardBody {
+                    title: Some(rd.reward_name.clone()),
+                    cost: Some(rd.cost as u64),
+                    is_enabled: Some(rd.is_active),
+                    is_user_input_required: Some(rd.is_input_required),
+                    ..Default::default()
+                };
+                match client.create_custom_reward(broadcaster_id, &body).await {
+                    Ok(created) => {
+                        // update DB to store the new Helix ID
+                        let mut updated_rd = rd.clone();
+                        updated_rd.reward_id = created.id;
+                        updated_rd.updated_at = Utc::now();
+                        redeem_service.redeem_repo.update_redeem(&updated_rd).await?;
+                        info!("Created new Helix reward for '{}' => new ID={}", rd.reward_name, updated_rd.reward_id);
+                    }
+                    Err(e) => {
+                        warn!("create_custom_reward => {e}");
+                    }
                 }
-                Err(e) => {
-                    warn!("create_custom_reward => {e}");
+            } else {
+                // If reward_id is set but Helix does not have it, we try to create
+                debug!("No Helix reward matching id='{}' => attempting create", rd.reward_id);
+                let body = CustomRewardBody {
+                    title: Some(rd.reward_name.clone()),
+                    cost: Some(rd.cost as u64),
+                    is_enabled: Some(rd.is_active),
+                    is_user_input_required: Some(rd.is_input_required),
+                    ..Default::default()
+                };
+                match client.create_custom_reward(broadcaster_id, &body).await {
+                    Ok(created) => {
+                        let mut updated_rd = rd.clone();
+                        updated_rd.reward_id = created.id;
+                        updated_rd.updated_at = Utc::now();
+                        redeem_service.redeem_repo.update_redeem(&updated_rd).await?;
+                        info!("Created Helix reward => updated DB for '{}'", rd.reward_name);
+                    }
+                    Err(e) => {
+                        warn!(
+                            "create_custom_reward => error for DB redeem '{}' => {e}",
+                            rd.reward_name
+                        );
+                    }
                 }
             }
-        } else {
-            // If reward_id is set but Helix does not have it, we try to create
-            debug!("No Helix reward matching id='{}' => attempting create", rd.reward_id);
+        }
+        Some(hrew) => {
+            // Scheduled redeems get their cost and availability from RedeemScheduleService
+            let cost_mismatch = !rd.dynamic_pricing && (rd.cost as u64) != hrew.cost;
+            let active_mismatch = !rd.dynamic_pricing && rd.is_active != hrew.is_enabled;
+            let input_mismatch = rd.is_input_required != hrew.is_user_input_required;
+
+            if cost_mismatch || active_mismatch || input_mismatch {
+                debug!(
+                    "Patching Helix => cost {}->{}, enabled {}->{}, input required {}->{}",
+                    hrew.cost, rd.cost, hrew.is_enabled, rd.is_active, 
+                    hrew.is_user_input_required, rd.is_input_required
+                );
+                let body = CustomRewardBody {
+                    cost: if cost_mismatch { Some(rd.cost as u64) } else { None },
+                    is_enabled: if active_mismatch { Some(rd.is_active) } else { None },
+                    is_user_input_required: if input_mismatch { Some(rd.is_input_required) } else { None },
+                    ..Default::default()
+                };
+                if let Err(e) = client.update_custom_reward(broadcaster_id, &rd.reward_id, &body).await {
+                    error!("update_custom_reward => {e}");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+// ----------------------------------------------------------------------------
+// On-demand sync with a preview of the drift between the bot and Twitch
+// ----------------------------------------------------------------------------
+
+/// Platform the bot stores channel point redeems under.
+pub(crate) const REDEEM_PLATFORM: &str = "twitch-eventsub";
+
+/// Which side wins when the bot and Twitch disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// Create and patch managed rewards on Twitch
+    ToTwitch,
+    /// Import Twitch rewards and copy their settings into the bot
+    FromTwitch,
+    /// Managed rewards are pushed, everything else is pulled
+    Both,
+}
+
+/// What a sync does about one redeem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedeemChangeKind {
+    /// A Twitch reward the bot has no redeem for
+    Import,
+    /// A redeem whose reward id is gone, matched to a Twitch reward by title
+    Link,
+    /// A managed redeem with no reward on Twitch
+    CreateOnTwitch,
+    /// Twitch is patched to match the bot's redeem
+    PushToTwitch,
+    /// The bot's redeem is updated to match Twitch
+    PullFromTwitch,
+    /// A redeem whose reward was deleted on Twitch and that isn't re-created.
+    /// Only reported, so its plugin or command binding isn't lost.
+    MissingOnTwitch,
+}
+
+impl std::fmt::Display for RedeemChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RedeemChangeKind::Import => "import",
+            RedeemChangeKind::Link => "link",
+            RedeemChangeKind::CreateOnTwitch => "create_on_twitch",
+            RedeemChangeKind::PushToTwitch => "push",
+            RedeemChangeKind::PullFromTwitch => "pull",
+            RedeemChangeKind::MissingOnTwitch => "missing_on_twitch",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The settings of a Twitch reward that the bot mirrors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardSnapshot {
+    pub id: String,
+    pub title: String,
+    pub cost: u64,
+    pub prompt: String,
+    pub is_enabled: bool,
+    pub is_user_input_required: bool,
+    /// Created by the bot's client id, so the bot may edit it
+    pub manageable: bool,
+}
+
+impl RewardSnapshot {
+    fn from_reward(reward: &CustomReward, manageable: bool) -> Self {
+        Self {
+            id: reward.id.clone(),
+            title: reward.title.clone(),
+            cost: reward.cost,
+            prompt: reward.prompt.clone(),
+            is_enabled: reward.is_enabled,
+            is_user_input_required: reward.is_user_input_required,
+            manageable,
+        }
+    }
+}
+
+/// One planned change, with what differs between the two sides.
+#[derive(Debug, Clone)]
+pub struct RedeemChange {
+    pub kind: RedeemChangeKind,
+    pub reward_name: String,
+    pub redeem: Option<Redeem>,
+    pub reward: Option<RewardSnapshot>,
+    /// e.g. "cost: 100 (bot) / 250 (Twitch)"
+    pub differences: Vec<String>,
+}
+
+/// A planned change and, unless it was a dry run, how applying it went.
+#[derive(Debug, Clone)]
+pub struct RedeemSyncResult {
+    pub change: RedeemChange,
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+fn prompt_of(rd: &Redeem) -> &str {
+    rd.redeem_prompt_text.as_deref().unwrap_or("")
+}
+
+/// Fields where the bot's redeem and the Twitch reward disagree.
+fn differences(rd: &Redeem, reward: &RewardSnapshot) -> Vec<String> {
+    let mut diffs = Vec::new();
+    let mut differ = |field: &str, ours: String, theirs: String| {
+        if ours != theirs {
+            diffs.push(format!("{}: {} (bot) / {} (Twitch)", field, ours, theirs));
+        }
+    };
+    differ("title", rd.reward_name.clone(), reward.title.clone());
+    // A scheduled redeem's cost and availability on Twitch are set by its schedules
+    if !rd.dynamic_pricing {
+        differ("cost", rd.cost.to_string(), reward.cost.to_string());
+        differ("enabled", rd.is_active.to_string(), reward.is_enabled.to_string());
+    }
+    differ("input required", rd.is_input_required.to_string(), reward.is_user_input_required.to_string());
+    differ("prompt", format!("{:?}", prompt_of(rd)), format!("{:?}", reward.prompt));
+    differ("managed", rd.is_managed.to_string(), reward.manageable.to_string());
+    diffs
+}
+
+/// Compares the bot's redeems with the channel's rewards.
+pub fn plan_changes(local: &[Redeem], rewards: &[RewardSnapshot], direction: SyncDirection) -> Vec<RedeemChange> {
+    let mut changes = Vec::new();
+    let by_id: HashMap<&str, &RewardSnapshot> = rewards.iter().map(|r| (r.id.as_str(), r)).collect();
+    let mut claimed: HashSet<&str> = local.iter()
+        .filter(|rd| by_id.contains_key(rd.reward_id.as_str()))
+        .map(|rd| rd.reward_id.as_str())
+        .collect();
+
+    for rd in local {
+        match by_id.get(rd.reward_id.as_str()) {
+            Some(reward) => {
+                let diffs = differences(rd, reward);
+                if diffs.is_empty() {
+                    continue;
+                }
+                // Only the managed flag differs; that's always taken from Twitch
+                let only_managed = diffs.len() == 1 && rd.is_managed != reward.manageable;
+                let push = rd.is_managed && reward.manageable && !only_managed;
+                let kind = match direction {
+                    SyncDirection::Both if push => RedeemChangeKind::PushToTwitch,
+                    SyncDirection::ToTwitch if push => RedeemChangeKind::PushToTwitch,
+                    SyncDirection::ToTwitch => continue,
+                    _ => RedeemChangeKind::PullFromTwitch,
+                };
+                changes.push(RedeemChange {
+                    kind,
+                    reward_name: rd.reward_name.clone(),
+                    redeem: Some(rd.clone()),
+                    reward: Some((*reward).clone()),
+                    differences: diffs,
+                });
+            }
+            None => {
+                // Twitch titles are unique per channel, so a same-titled reward no
+                // other redeem points at is this redeem's reward re-created
+                let same_title = rewards.iter().find(|r| {
+                    !claimed.contains(r.id.as_str()) && r.title.eq_ignore_ascii_case(&rd.reward_name)
+                });
+                if let Some(reward) = same_title {
+                    claimed.insert(reward.id.as_str());
+                    let mut diffs = vec![format!("reward id: {:?} (bot) / {:?} (Twitch)", rd.reward_id, reward.id)];
+                    diffs.extend(differences(rd, reward));
+                    changes.push(RedeemChange {
+                        kind: RedeemChangeKind::Link,
+                        reward_name: rd.reward_name.clone(),
+                        redeem: Some(rd.clone()),
+                        reward: Some(reward.clone()),
+                        differences: diffs,
+                    });
+                    continue;
+                }
+                let kind = if rd.is_managed && direction != SyncDirection::FromTwitch {
+                    RedeemChangeKind::CreateOnTwitch
+                } else {
+                    RedeemChangeKind::MissingOnTwitch
+                };
+                changes.push(RedeemChange {
+                    kind,
+                    reward_name: rd.reward_name.clone(),
+                    redeem: Some(rd.clone()),
+                    reward: None,
+                    differences: Vec::new(),
+                });
+            }
+        }
+    }
+
+    if direction != SyncDirection::ToTwitch {
+        for reward in rewards.iter().filter(|r| !claimed.contains(r.id.as_str())) {
+            changes.push(RedeemChange {
+                kind: RedeemChangeKind::Import,
+                reward_name: reward.title.clone(),
+                redeem: None,
+                reward: Some(reward.clone()),
+                differences: Vec::new(),
+            });
+        }
+    }
+    changes
+}
+
+/// The broadcaster's rewards, flagged with whether the bot may edit them.
+async fn fetch_rewards(client: &TwitchHelixClient, broadcaster_id: &str) -> Result<Vec<RewardSnapshot>, Error> {
+    // Errors are returned rather than treated as "no rewards", which would
+    // make every redeem look deleted on Twitch
+    let all = client.get_custom_rewards(broadcaster_id, None, false).await?;
+    let manageable: HashSet<String> = client.get_custom_rewards(broadcaster_id, None, true).await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    Ok(all.iter().map(|r| RewardSnapshot::from_reward(r, manageable.contains(&r.id))).collect())
+}
+
+/// Copies a reward's settings into a redeem.
+fn pull_into(rd: &Redeem, reward: &RewardSnapshot) -> Redeem {
+    let mut updated = rd.clone();
+    updated.reward_id = reward.id.clone();
+    updated.reward_name = reward.title.clone();
+    if !rd.dynamic_pricing {
+        updated.cost = reward.cost as i32;
+        updated.is_active = reward.is_enabled;
+    }
+    updated.is_input_required = reward.is_user_input_required;
+    updated.redeem_prompt_text = Some(reward.prompt.clone()).filter(|p| !p.is_empty());
+    updated.is_managed = reward.manageable;
+    updated.updated_at = Utc::now();
+    updated
+}
+
+async fn apply_change(
+    change: &RedeemChange,
+    redeem_repo: &dyn RedeemRepository,
+    client: &TwitchHelixClient,
+    broadcaster_id: &str,
+) -> Result<bool, Error> {
+    match (change.kind, &change.redeem, &change.reward) {
+        (RedeemChangeKind::Import, _, Some(reward)) => {
+            let now = Utc::now();
+            let new_redeem = Redeem {
+                redeem_id: Uuid::new_v4(),
+                platform: REDEEM_PLATFORM.to_string(),
+                reward_id: reward.id.clone(),
+                reward_name: reward.title.clone(),
+                cost: reward.cost as i32,
+                is_active: reward.is_enabled,
+                dynamic_pricing: false,
+                active_offline: false,
+                is_managed: reward.manageable,
+                plugin_name: None,
+                command_name: None,
+                created_at: now,
+                updated_at: now,
+                active_credential_id: None,
+                is_input_required: reward.is_user_input_required,
+                redeem_prompt_text: Some(reward.prompt.clone()).filter(|p| !p.is_empty()),
+                requires_approval: false,
+            };
+            redeem_repo.create_redeem(&new_redeem).await?;
+            Ok(true)
+        }
+        (RedeemChangeKind::Link | RedeemChangeKind::PullFromTwitch, Some(rd), Some(reward)) => {
+            redeem_repo.update_redeem(&pull_into(rd, reward)).await?;
+            Ok(true)
+        }
+        (RedeemChangeKind::PushToTwitch, Some(rd), Some(reward)) => {
+            let body = CustomRewardBody {
+                title: Some(rd.reward_name.clone()).filter(|t| *t != reward.title),
+                cost: Some(rd.cost as u64).filter(|c| !rd.dynamic_pricing && *c != reward.cost),
+                prompt: Some(prompt_of(rd).to_string()).filter(|p| *p != reward.prompt),
+                is_enabled: Some(rd.is_active).filter(|e| !rd.dynamic_pricing && *e != reward.is_enabled),
+                is_user_input_required: Some(rd.is_input_required).filter(|i| *i != reward.is_user_input_required),
+                ..Default::default()
+            };
+            client.update_custom_reward(broadcaster_id, &reward.id, &body).await?;
+            Ok(true)
+        }
+        (RedeemChangeKind::CreateOnTwitch, Some(rd), _) => {
             let body = CustomRewardBody {
                 title: Some(rd.reward_name.clone()),
                 cost: Some(rd.cost as u64),
+                prompt: rd.redeem_prompt_text.clone().filter(|p| !p.is_empty()),
                 is_enabled: Some(rd.is_active),
                 is_user_input_required: Some(rd.is_input_required),
                 ..Default::default()
             };
-            match client.create_custom_reward(broadcaster_id, &body).await {
-                Ok(created) => {
-                    let mut updated_rd = rd.clone();
-                    updated_rd.reward_id = created.id;
-                    updated_rd.updated_at = Utc::now();
-                    redeem_service.redeem_repo.update_redeem(&updated_rd).await?;
-                    info!("Created Helix reward => updated DB for '{}'", rd.reward_name);
-                }
-                Err(e) => {
-                    warn!(
-                        "create_custom_reward => error for DB redeem '{}' => {e}",
-                        rd.reward_name
-                    );
-                }
-            }
+            let created = client.create_custom_reward(broadcaster_id, &body).await?;
+            let mut updated = rd.clone();
+            updated.reward_id = created.id;
+            updated.updated_at = Utc::now();
+            redeem_repo.update_redeem(&updated).await?;
+            Ok(true)
         }
-    } else {
-        // Helix reward does exist, check if we need to patch cost, enabled or user input
-        let hrew = maybe_helix_rd.unwrap();
-        let cost_mismatch = (rd.cost as u64) != hrew.cost;
-        let active_mismatch = rd.is_active != hrew.is_enabled;
-        let input_mismatch = rd.is_input_required != hrew.is_user_input_required;
-
-        if cost_mismatch || active_mismatch || input_mismatch {
-            debug!(
-                "Patching Helix => cost {}->{}, enabled {}->{}, input required {}->{}",
-                hrew.cost, rd.cost, hrew.is_enabled, rd.is_active, 
-                hrew.is_user_input_required, rd.is_input_required
-            );
-            let body = CustomRewardBody {
-                cost: if cost_mismatch { Some(rd.cost as u64) } else { None },
-                is_enabled: if active_mismatch { Some(rd.is_active) } else { None },
-                is_user_input_required: if input_mismatch { Some(rd.is_input_required) } else { None },
-                ..Default::default()
-            };
-            if let Err(e) = client.update_custom_reward(broadcaster_id, &rd.reward_id, &body).await {
-                error!("update_custom_reward => {e}");
+        _ => Ok(false),
+    }
+}
+
+/// Compares the bot's redeems with the broadcaster's rewards and, unless
+/// `dry_run`, applies the changes. A failed change doesn't stop the others.
+pub async fn sync_redeems_with_twitch(
+    redeem_repo: &dyn RedeemRepository,
+    credentials_repo: &(dyn CredentialsRepository + Send + Sync),
+    direction: SyncDirection,
+    dry_run: bool,
+) -> Result<Vec<RedeemSyncResult>, Error> {
+    let helix = broadcaster_helix(credentials_repo).await?;
+    let rewards = fetch_rewards(&helix.client, &helix.broadcaster_id).await?;
+    let local = redeem_repo.list_redeems(REDEEM_PLATFORM).await?;
+    let changes = plan_changes(&local, &rewards, direction);
+    info!(
+        "[redeem_sync] {} redeems, {} Twitch rewards => {} changes (direction={:?}, dry_run={})",
+        local.len(), rewards.len(), changes.len(), direction, dry_run
+    );
+
+    let mut results = Vec::with_capacity(changes.len());
+    for change in changes {
+        if dry_run {
+            results.push(RedeemSyncResult { change, applied: false, error: None });
+            continue;
+        }
+        let result = match apply_change(&change, redeem_repo, &helix.client, &helix.broadcaster_id).await {
+            Ok(applied) => RedeemSyncResult { change, applied, error: None },
+            Err(e) => {
+                warn!("[redeem_sync] {} '{}' failed: {}", change.kind, change.reward_name, e);
+                RedeemSyncResult { change, applied: false, error: Some(e.to_string()) }
             }
+        };
+        results.push(result);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redeem(name: &str, reward_id: &str, cost: i32, managed: bool) -> Redeem {
+        Redeem {
+            redeem_id: Uuid::new_v4(),
+            platform: REDEEM_PLATFORM.to_string(),
+            reward_id: reward_id.to_string(),
+            reward_name: name.to_string(),
+            cost,
+            is_active: true,
+            dynamic_pricing: false,
+            active_offline: false,
+            is_managed: managed,
+            plugin_name: None,
+            command_name: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            active_credential_id: None,
+            is_input_required: false,
+            redeem_prompt_text: None,
+            requires_approval: false,
         }
     }
 
-    Ok(())
-}
\ No newline at end of file
+    fn reward(id: &str, title: &str, cost: u64, manageable: bool) -> RewardSnapshot {
+        RewardSnapshot {
+            id: id.to_string(),
+            title: title.to_string(),
+            cost,
+            prompt: String::new(),
+            is_enabled: true,
+            is_user_input_required: false,
+            manageable,
+        }
+    }
+
+    fn kinds(changes: &[RedeemChange]) -> Vec<(RedeemChangeKind, &str)> {
+        changes.iter().map(|c| (c.kind, c.reward_name.as_str())).collect()
+    }
+
+    #[test]
+    fn test_plans_drift_both_ways() {
+        let local = vec![
+            redeem("Hydrate", "r1", 100, true),      // bot is newer => push
+            redeem("Song", "r2", 500, false),        // changed on Twitch => pull
+            redeem("Same", "r3", 50, false),         // in sync
+            redeem("Gone", "old", 10, false),        // deleted on Twitch
+            redeem("Mine", "", 10, true),            // never created
+            redeem("Renamed", "stale", 20, false),   // re-created on Twitch
+        ];
+        let rewards = vec![
+            reward("r1", "Hydrate", 200, true),
+            reward("r2", "Song", 750, false),
+            reward("r3", "Same", 50, false),
+            reward("r4", "renamed", 20, false),
+            reward("r5", "Brand new", 1000, false),
+        ];
+
+        let both = plan_changes(&local, &rewards, SyncDirection::Both);
+        assert_eq!(kinds(&both), vec![
+            (RedeemChangeKind::PushToTwitch, "Hydrate"),
+            (RedeemChangeKind::PullFromTwitch, "Song"),
+            (RedeemChangeKind::MissingOnTwitch, "Gone"),
+            (RedeemChangeKind::CreateOnTwitch, "Mine"),
+            (RedeemChangeKind::Link, "Renamed"),
+            (RedeemChangeKind::Import, "Brand new"),
+        ]);
+        assert_eq!(both[0].differences, vec!["cost: 100 (bot) / 200 (Twitch)"]);
+
+        let to_twitch = plan_changes(&local, &rewards, SyncDirection::ToTwitch);
+        assert!(to_twitch.iter().all(|c| c.kind != RedeemChangeKind::Import && c.kind != RedeemChangeKind::PullFromTwitch));
+
+        let from_twitch = plan_changes(&local, &rewards, SyncDirection::FromTwitch);
+        assert_eq!(from_twitch[0].kind, RedeemChangeKind::PullFromTwitch);
+        assert!(from_twitch.iter().all(|c| c.kind != RedeemChangeKind::CreateOnTwitch));
+    }
+}
@@ -0,0 +1,263 @@
+// File: maowbot-core/src/tasks/retention.rs
+//
+// Data retention: prunes the tables that grow without bound (chat archive,
+// event journal, command usage, AI memories) by age and by row count. Limits
+// come from the `retention.*` settings; chat age keeps following each
+// channel's own policy (`chatlog retention`). Biweekly maintenance applies
+// them, and `system db retention` shows what a run would delete first.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::{error, info};
+
+use maowbot_common::traits::repository_traits::ChatArchiveRepository;
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// A table the retention engine prunes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionTarget {
+    ChatMessages,
+    EventJournal,
+    CommandUsage,
+    AiMemory,
+}
+
+impl RetentionTarget {
+    pub const ALL: [RetentionTarget; 4] = [
+        RetentionTarget::ChatMessages,
+        RetentionTarget::EventJournal,
+        RetentionTarget::CommandUsage,
+        RetentionTarget::AiMemory,
+    ];
+
+    pub fn table(self) -> &'static str {
+        match self {
+            RetentionTarget::ChatMessages => "chat_messages",
+            RetentionTarget::EventJournal => "bot_events",
+            RetentionTarget::CommandUsage => "command_usage",
+            RetentionTarget::AiMemory => "ai_memory",
+        }
+    }
+
+    pub fn time_column(self) -> &'static str {
+        match self {
+            RetentionTarget::ChatMessages => "timestamp",
+            RetentionTarget::EventJournal => "event_timestamp",
+            RetentionTarget::CommandUsage => "executed_at",
+            RetentionTarget::AiMemory => "timestamp",
+        }
+    }
+
+    /// The setting holding the age limit; chat uses its per-channel policies instead.
+    fn days_key(self) -> Option<&'static str> {
+        match self {
+            RetentionTarget::ChatMessages => None,
+            RetentionTarget::EventJournal => Some("retention.bot_events_days"),
+            RetentionTarget::CommandUsage => Some("retention.command_usage_days"),
+            RetentionTarget::AiMemory => Some("retention.ai_memory_days"),
+        }
+    }
+
+    fn max_rows_key(self) -> &'static str {
+        match self {
+            RetentionTarget::ChatMessages => "retention.chat_messages_max_rows",
+            RetentionTarget::EventJournal => "retention.bot_events_max_rows",
+            RetentionTarget::CommandUsage => "retention.command_usage_max_rows",
+            RetentionTarget::AiMemory => "retention.ai_memory_max_rows",
+        }
+    }
+}
+
+/// The limits for one table; `None` means unlimited.
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    pub target: RetentionTarget,
+    /// For chat, the default for channels without their own policy
+    pub max_age_days: Option<i64>,
+    pub max_rows: Option<i64>,
+}
+
+/// What a run deleted, or would delete on a dry run.
+#[derive(Debug, Clone)]
+pub struct RetentionEntry {
+    pub policy: RetentionPolicy,
+    /// Rows in the table before the run
+    pub total_rows: i64,
+    pub by_age: u64,
+    /// Rows past `max_rows` once the age limit was applied. On a dry run chat
+    /// rows can be counted under both, since channels expire at different ages.
+    pub by_size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub entries: Vec<RetentionEntry>,
+    /// Tables that couldn't be checked or pruned, and why
+    pub errors: Vec<String>,
+    pub ran_at: DateTime<Utc>,
+}
+
+impl RetentionReport {
+    pub fn total_deleted(&self) -> u64 {
+        self.entries.iter().map(|e| e.by_age + e.by_size).sum()
+    }
+}
+
+/// Row counts and deletes for the retention engine.
+#[async_trait]
+pub trait RetentionRepository: Send + Sync {
+    async fn count_rows(&self, target: RetentionTarget) -> Result<i64, Error>;
+    /// Rows older than `max_age_days` (0 = no age limit); for AI memories also
+    /// the ones past `expires_at`. Counts instead of deleting on a dry run.
+    async fn prune_by_age(&self, target: RetentionTarget, max_age_days: i32, dry_run: bool) -> Result<u64, Error>;
+    /// Everything older than the newest `max_rows` rows, counting only rows
+    /// newer than `kept_age_days` (0 = all of them).
+    async fn prune_by_size(&self, target: RetentionTarget, max_rows: i64, kept_age_days: i32, dry_run: bool)
+        -> Result<u64, Error>;
+}
+
+pub struct RetentionEngine {
+    repo: Arc<dyn RetentionRepository>,
+    chat_archive: Arc<dyn ChatArchiveRepository>,
+    settings: Arc<SettingsRegistry>,
+}
+
+impl RetentionEngine {
+    pub fn new(
+        repo: Arc<dyn RetentionRepository>,
+        chat_archive: Arc<dyn ChatArchiveRepository>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self { repo, chat_archive, settings }
+    }
+
+    /// The current limits for every table. A limit of 0 in the settings means none.
+    pub fn policies(&self) -> Vec<RetentionPolicy> {
+        policies_from(|key| self.settings.get_i64(key))
+    }
+
+    /// Applies every policy, or with `dry_run` only counts what would go.
+    /// A table that fails is reported and the rest still run.
+    pub async fn run(&self, dry_run: bool) -> Result<RetentionReport, Error> {
+        let mut report = RetentionReport { dry_run, entries: Vec::new(), errors: Vec::new(), ran_at: Utc::now() };
+        for policy in self.policies() {
+            match self.apply(&policy, dry_run).await {
+                Ok(entry) => report.entries.push(entry),
+                Err(e) => {
+                    error!("Retention for {} failed: {:?}", policy.target.table(), e);
+                    report.errors.push(format!("{}: {}", policy.target.table(), e));
+                }
+            }
+        }
+        if !dry_run {
+            info!(
+                "Retention removed {} rows ({})",
+                report.total_deleted(),
+                report.entries.iter()
+                    .map(|e| format!("{} {}", e.policy.target.table(), e.by_age + e.by_size))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        Ok(report)
+    }
+
+    async fn apply(&self, policy: &RetentionPolicy, dry_run: bool) -> Result<RetentionEntry, Error> {
+        let target = policy.target;
+        let total_rows = self.repo.count_rows(target).await?;
+
+        let by_age = match (target, policy.max_age_days) {
+            (RetentionTarget::ChatMessages, Some(days)) => {
+                if dry_run {
+                    self.chat_archive.count_expired_messages(days).await?
+                } else {
+                    self.chat_archive.purge_expired_messages(days).await?
+                }
+            }
+            // Faded AI memories (past `expires_at`) go even without an age limit
+            (_, None) if target != RetentionTarget::AiMemory => 0,
+            (_, days) => self.repo.prune_by_age(target, days.unwrap_or(0) as i32, dry_run).await?,
+        };
+
+        let by_size = match policy.max_rows {
+            // On a dry run the age pass hasn't deleted anything yet, so its rows
+            // are left out of the size count
+            Some(max_rows) => {
+                self.repo.prune_by_size(target, max_rows, size_pass_age_days(policy, dry_run), dry_run).await?
+            }
+            None => 0,
+        };
+
+        Ok(RetentionEntry { policy: policy.clone(), total_rows, by_age, by_size })
+    }
+}
+
+fn policies_from(setting: impl Fn(&str) -> Option<i64>) -> Vec<RetentionPolicy> {
+    let limit = |key: &str| setting(key).filter(|n| *n > 0);
+    RetentionTarget::ALL.iter()
+        .map(|&target| RetentionPolicy {
+            target,
+            max_age_days: match target.days_key() {
+                Some(key) => limit(key),
+                None => limit("chat_logging.default_retention_days"),
+            },
+            max_rows: limit(target.max_rows_key()),
+        })
+        .collect()
+}
+
+/// Age limit the size pass leaves out of its count (0 = none).
+fn size_pass_age_days(policy: &RetentionPolicy, dry_run: bool) -> i32 {
+    match (dry_run, policy.target) {
+        (true, RetentionTarget::ChatMessages) | (false, _) => 0,
+        (true, _) => policy.max_age_days.unwrap_or(0) as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_zero_or_missing_limits_mean_unlimited() {
+        let settings: HashMap<&str, i64> = HashMap::from([
+            ("chat_logging.default_retention_days", 90),
+            ("retention.bot_events_days", 0),
+            ("retention.command_usage_days", 30),
+            ("retention.ai_memory_max_rows", 1000),
+        ]);
+        let policies = policies_from(|key| settings.get(key).copied());
+        let get = |t: RetentionTarget| policies.iter().find(|p| p.target == t).unwrap().clone();
+
+        assert_eq!(policies.len(), RetentionTarget::ALL.len());
+        assert_eq!(get(RetentionTarget::ChatMessages).max_age_days, Some(90));
+        assert_eq!(get(RetentionTarget::EventJournal).max_age_days, None);
+        assert_eq!(get(RetentionTarget::CommandUsage).max_age_days, Some(30));
+        assert_eq!(get(RetentionTarget::AiMemory).max_rows, Some(1000));
+        assert_eq!(get(RetentionTarget::AiMemory).max_age_days, None);
+    }
+
+    #[test]
+    fn test_dry_run_size_pass_skips_rows_the_age_pass_would_take() {
+        let policy = |target| RetentionPolicy { target, max_age_days: Some(14), max_rows: Some(10) };
+        assert_eq!(size_pass_age_days(&policy(RetentionTarget::EventJournal), true), 14);
+        assert_eq!(size_pass_age_days(&policy(RetentionTarget::EventJournal), false), 0);
+        // Chat expires per channel, so its dry-run count can overlap
+        assert_eq!(size_pass_age_days(&policy(RetentionTarget::ChatMessages), true), 0);
+
+        let report = RetentionReport {
+            dry_run: true,
+            entries: vec![
+                RetentionEntry { policy: policy(RetentionTarget::EventJournal), total_rows: 50, by_age: 5, by_size: 3 },
+                RetentionEntry { policy: policy(RetentionTarget::CommandUsage), total_rows: 9, by_age: 2, by_size: 0 },
+            ],
+            errors: Vec::new(),
+            ran_at: Utc::now(),
+        };
+        assert_eq!(report.total_deleted(), 10);
+    }
+}
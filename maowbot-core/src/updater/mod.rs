@@ -0,0 +1,535 @@
+// File: maowbot-core/src/updater/mod.rs
+//
+// Self-update: polls a release feed, downloads the binary for this platform,
+// checks its SHA-256 digest and Ed25519 signature, swaps it in next to the
+// running executable and asks the server to restart into it.
+//
+// Feed format (JSON, served over https from `updater.feed_url`):
+//   {
+//     "version": "0.5.0",
+//     "published_at": "2026-10-01T12:00:00Z",
+//     "notes": "...",
+//     "assets": [
+//       { "target": "x86_64-linux", "url": "https://.../maowbot",
+//         "sha256": "<hex>", "signature": "<base64 Ed25519 signature>" }
+//     ]
+//   }
+//
+// The signature covers `signed_payload`: the version and target along with the
+// digest, so an older signed build can't be served under a newer version (or
+// for another platform) by whoever controls the feed or the connection.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use ring::digest::{digest, SHA256};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::eventbus::{BotEvent, EventBus};
+use crate::settings::SettingsRegistry;
+use crate::Error;
+
+/// Written before an update restart, read (and removed) by the new process.
+pub const RESTART_STATE_PATH: &str = "restart_state.json";
+
+/// Delay before the first scheduled check, so startup isn't slowed down.
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    pub version: String,
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub notes: String,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    /// "<arch>-<os>", e.g. "x86_64-linux" or "x86_64-windows"
+    pub target: String,
+    pub url: String,
+    pub sha256: String,
+    pub signature: String,
+}
+
+impl ReleaseManifest {
+    pub fn asset_for_current_target(&self) -> Option<&ReleaseAsset> {
+        let target = current_target();
+        self.assets.iter().find(|a| a.target.eq_ignore_ascii_case(&target))
+    }
+}
+
+/// The feed target name of the running binary.
+pub fn current_target() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+/// Compares dotted numeric versions ("0.4.10" > "0.4.9"); a leading "v" and
+/// any pre-release/build suffix are ignored.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(v: &str) -> Vec<u64> {
+        v.trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    }
+    let (a, b) = (parts(candidate), parts(current));
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let (x, y) = (a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0));
+        if x != y {
+            return x > y;
+        }
+    }
+    false
+}
+
+/// What a release asset's signature is made over: the release version, the
+/// target and the binary's SHA-256 digest (lowercase hex), one per line.
+pub fn signed_payload(version: &str, target: &str, sha256: &str) -> String {
+    format!("maowbot-release\n{}\n{}\n{}\n", version.trim(), target.trim().to_lowercase(), sha256.trim().to_lowercase())
+}
+
+/// Feeds and downloads must come over https; the signature doesn't cover the
+/// notes or which assets are offered.
+fn require_https(url: &str, what: &str) -> Result<(), Error> {
+    match reqwest::Url::parse(url.trim()) {
+        Ok(parsed) if parsed.scheme() == "https" => Ok(()),
+        Ok(_) => Err(Error::ValidationError(format!("{} must be an https URL: {}", what, url))),
+        Err(e) => Err(Error::ValidationError(format!("Invalid {} '{}': {}", what, url, e))),
+    }
+}
+
+/// Checks a downloaded binary against the digest and signature in its
+/// manifest entry, for the release `version`.
+pub fn verify_release(bytes: &[u8], version: &str, asset: &ReleaseAsset, public_key_b64: &str) -> Result<(), Error> {
+    let actual: String = digest(&SHA256, bytes)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if !actual.eq_ignore_ascii_case(asset.sha256.trim()) {
+        return Err(Error::ValidationError(format!(
+            "SHA-256 mismatch: expected {}, got {}", asset.sha256, actual
+        )));
+    }
+
+    let public_key = BASE64.decode(public_key_b64.trim())
+        .map_err(|e| Error::ValidationError(format!("Invalid updater.public_key: {}", e)))?;
+    let signature = BASE64.decode(asset.signature.trim())
+        .map_err(|e| Error::ValidationError(format!("Invalid release signature encoding: {}", e)))?;
+    UnparsedPublicKey::new(&ED25519, &public_key)
+        .verify(signed_payload(version, &asset.target, &actual).as_bytes(), &signature)
+        .map_err(|_| Error::ValidationError("Release signature does not match updater.public_key".into()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdatePhase {
+    Idle,
+    Checking,
+    UpToDate,
+    Available,
+    Downloading,
+    /// New binary verified and swapped in; waiting for the restart
+    Staged,
+    Failed,
+}
+
+impl std::fmt::Display for UpdatePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            UpdatePhase::Idle => "idle",
+            UpdatePhase::Checking => "checking",
+            UpdatePhase::UpToDate => "up-to-date",
+            UpdatePhase::Available => "available",
+            UpdatePhase::Downloading => "downloading",
+            UpdatePhase::Staged => "staged",
+            UpdatePhase::Failed => "failed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateStatus {
+    pub phase: UpdatePhase,
+    pub current_version: String,
+    pub latest: Option<ReleaseManifest>,
+    pub last_checked: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// What an update restart should bring back up in the new process.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestartState {
+    pub from_version: String,
+    pub to_version: String,
+    /// (platform, account_name) of every runtime that was running
+    pub runtimes: Vec<(String, String)>,
+}
+
+impl RestartState {
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Reads and removes the state file, if an update restart left one.
+    pub fn take(path: &Path) -> Option<Self> {
+        let raw = std::fs::read(path).ok()?;
+        let _ = std::fs::remove_file(path);
+        match serde_json::from_slice(&raw) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                warn!("Ignoring unreadable restart state {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+pub struct Updater {
+    settings: Arc<SettingsRegistry>,
+    event_bus: Arc<EventBus>,
+    http: reqwest::Client,
+    exe_path: PathBuf,
+    status: Mutex<UpdateStatus>,
+    /// Held for a whole check/download/install so two can't interleave
+    busy: tokio::sync::Mutex<()>,
+    restart_requested: AtomicBool,
+}
+
+impl Updater {
+    pub fn new(settings: Arc<SettingsRegistry>, event_bus: Arc<EventBus>, current_version: &str) -> Result<Self, Error> {
+        Ok(Self {
+            settings,
+            event_bus,
+            http: reqwest::Client::builder()
+                .user_agent(format!("maowbot/{}", current_version))
+                .timeout(Duration::from_secs(300))
+                .build()?,
+            exe_path: std::env::current_exe()?,
+            status: Mutex::new(UpdateStatus {
+                phase: UpdatePhase::Idle,
+                current_version: current_version.to_string(),
+                latest: None,
+                last_checked: None,
+                last_error: None,
+            }),
+            busy: tokio::sync::Mutex::new(()),
+            restart_requested: AtomicBool::new(false),
+        })
+    }
+
+    pub fn status(&self) -> UpdateStatus {
+        self.status.lock().clone()
+    }
+
+    pub fn current_version(&self) -> String {
+        self.status.lock().current_version.clone()
+    }
+
+    /// Set once a new binary is in place; the server relaunches instead of exiting.
+    pub fn restart_requested(&self) -> bool {
+        self.restart_requested.load(Ordering::SeqCst)
+    }
+
+    /// Fetches the feed. Returns the release if it is newer than the running version.
+    pub async fn check(&self) -> Result<Option<ReleaseManifest>, Error> {
+        let _busy = self.busy.try_lock()
+            .map_err(|_| Error::Internal("An update is already in progress".into()))?;
+        self.check_locked().await
+    }
+
+    async fn check_locked(&self) -> Result<Option<ReleaseManifest>, Error> {
+        let feed_url = self.settings.get("updater.feed_url")
+            .filter(|u| !u.trim().is_empty())
+            .ok_or_else(|| Error::NotFound("No release feed configured (set updater.feed_url)".into()))?;
+
+        require_https(&feed_url, "updater.feed_url").map_err(|e| self.fail(e))?;
+        self.set_phase(UpdatePhase::Checking);
+        let manifest = match self.fetch_manifest(&feed_url).await {
+            Ok(m) => m,
+            Err(e) => return Err(self.fail(e)),
+        };
+
+        let newer = is_newer(&manifest.version, &self.current_version());
+        {
+            let mut status = self.status.lock();
+            status.last_checked = Some(Utc::now());
+            status.last_error = None;
+            status.phase = if newer { UpdatePhase::Available } else { UpdatePhase::UpToDate };
+            status.latest = Some(manifest.clone());
+        }
+        Ok(newer.then_some(manifest))
+    }
+
+    /// Checks the feed, then downloads, verifies and installs a newer release.
+    /// Returns the installed version, or `None` when already up to date.
+    pub async fn download_and_install(&self) -> Result<Option<String>, Error> {
+        let _busy = self.busy.try_lock()
+            .map_err(|_| Error::Internal("An update is already in progress".into()))?;
+        if self.restart_requested() {
+            return Err(Error::Internal("An update is already installed and waiting for a restart".into()));
+        }
+
+        let Some(manifest) = self.check_locked().await? else {
+            return Ok(None);
+        };
+        let asset = manifest.asset_for_current_target()
+            .ok_or_else(|| self.fail(Error::NotFound(format!(
+                "Release {} has no build for {}", manifest.version, current_target()
+            ))))?
+            .clone();
+        let public_key = self.settings.get("updater.public_key")
+            .filter(|k| !k.trim().is_empty())
+            .ok_or_else(|| self.fail(Error::ValidationError(
+                "Refusing to install an unsigned update: set updater.public_key".into()
+            )))?;
+
+        require_https(&asset.url, "The release download").map_err(|e| self.fail(e))?;
+
+        self.set_phase(UpdatePhase::Downloading);
+        info!("Updater: downloading {} from {}", manifest.version, asset.url);
+        let bytes = match self.download(&asset.url).await {
+            Ok(b) => b,
+            Err(e) => return Err(self.fail(e)),
+        };
+        verify_release(&bytes, &manifest.version, &asset, &public_key).map_err(|e| self.fail(e))?;
+        self.install(&bytes).map_err(|e| self.fail(e))?;
+
+        self.set_phase(UpdatePhase::Staged);
+        self.restart_requested.store(true, Ordering::SeqCst);
+        info!("Updater: {} verified and installed at {}", manifest.version, self.exe_path.display());
+        Ok(Some(manifest.version))
+    }
+
+    async fn fetch_manifest(&self, feed_url: &str) -> Result<ReleaseManifest, Error> {
+        let response = self.http.get(feed_url).send().await?.error_for_status()?;
+        Ok(response.json::<ReleaseManifest>().await?)
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let response = self.http.get(url).send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Writes the new binary beside the running one, then swaps them. The
+    /// previous binary is kept as `<exe>.old` for a manual rollback.
+    fn install(&self, bytes: &[u8]) -> Result<(), Error> {
+        let file_name = self.exe_path.file_name()
+            .ok_or_else(|| Error::Internal("Executable path has no file name".into()))?
+            .to_string_lossy()
+            .to_string();
+        let staged = self.exe_path.with_file_name(format!("{}.new", file_name));
+        let backup = self.exe_path.with_file_name(format!("{}.old", file_name));
+
+        std::fs::write(&staged, bytes)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        let _ = std::fs::remove_file(&backup);
+        std::fs::rename(&self.exe_path, &backup)?;
+        if let Err(e) = std::fs::rename(&staged, &self.exe_path) {
+            // Put the running binary back so the next start still works
+            let _ = std::fs::rename(&backup, &self.exe_path);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Shuts the server down after `grace`; it relaunches into the new binary on the way out.
+    pub fn schedule_restart(&self, grace: Duration) {
+        let event_bus = self.event_bus.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            info!("Updater: restarting into the new version");
+            event_bus.shutdown();
+        });
+    }
+
+    /// Replaces this process with the (updated) executable, passing the same arguments.
+    /// Only returns on failure (or on Windows, once the new process has been spawned).
+    pub fn relaunch(&self) -> Result<(), Error> {
+        let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+        let mut command = std::process::Command::new(&self.exe_path);
+        command.args(&args);
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            Err(command.exec().into())
+        }
+        #[cfg(not(unix))]
+        {
+            command.spawn()?;
+            Ok(())
+        }
+    }
+
+    /// Checks the feed every `updater.check_interval_hours`. New releases are
+    /// announced as a SystemMessage and, with `updater.auto_update`, installed
+    /// and restarted into after a one-minute warning.
+    pub fn spawn_schedule(self: &Arc<Self>) -> JoinHandle<()> {
+        let weak = Arc::downgrade(self);
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        tokio::spawn(async move {
+            let mut delay = FIRST_CHECK_DELAY;
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {
+                        let Some(updater) = weak.upgrade() else { break };
+                        let hours = updater.settings.get_u64("updater.check_interval_hours").unwrap_or(24);
+                        delay = Duration::from_secs(hours.max(1) * 3600);
+                        if updater.settings.get("updater.feed_url").map(|u| u.trim().is_empty()).unwrap_or(true) {
+                            continue;
+                        }
+                        updater.scheduled_check().await;
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            info!("Updater schedule: shutting down cleanly.");
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    async fn scheduled_check(&self) {
+        let auto_update = self.settings.get_bool("updater.auto_update").unwrap_or(false);
+        if !auto_update {
+            match self.check().await {
+                Ok(Some(release)) => {
+                    self.event_bus.publish(BotEvent::SystemMessage(format!(
+                        "MaowBot {} is available (running {}). Install it with `system update apply`.",
+                        release.version, self.current_version()
+                    ))).await;
+                }
+                Ok(None) => {}
+                Err(e) => error!("Updater: scheduled check failed: {}", e),
+            }
+            return;
+        }
+
+        match self.download_and_install().await {
+            Ok(Some(version)) => {
+                self.event_bus.publish(BotEvent::SystemMessage(format!(
+                    "MaowBot {} installed; restarting in 60 seconds.", version
+                ))).await;
+                self.schedule_restart(Duration::from_secs(60));
+            }
+            Ok(None) => {}
+            Err(e) => error!("Updater: automatic update failed: {}", e),
+        }
+    }
+
+    fn set_phase(&self, phase: UpdatePhase) {
+        self.status.lock().phase = phase;
+    }
+
+    /// Records a failure in the status and hands the error back.
+    fn fail(&self, e: Error) -> Error {
+        let mut status = self.status.lock();
+        status.phase = UpdatePhase::Failed;
+        status.last_error = Some(e.to_string());
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.4.10", "0.4.9"));
+        assert!(is_newer("v1.0.0", "0.9.9"));
+        assert!(is_newer("1.0.1", "1.0"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("1.0.0-rc1", "1.0.0"));
+        assert!(!is_newer("0.3.0", "0.4.0"));
+    }
+
+    #[test]
+    fn test_verify_rejects_digest_mismatch() {
+        let asset = ReleaseAsset {
+            target: current_target(),
+            url: String::new(),
+            sha256: "00".repeat(32),
+            signature: String::new(),
+        };
+        let err = verify_release(b"not the release", "1.0.0", &asset, "").unwrap_err();
+        assert!(err.to_string().contains("SHA-256 mismatch"));
+    }
+
+    /// A release signed with a fresh test key: (public key, asset for "1.2.0").
+    fn signed_release(bytes: &[u8]) -> (String, ReleaseAsset) {
+        use ring::rand::SystemRandom;
+        use ring::signature::{Ed25519KeyPair, KeyPair};
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let sha256: String = digest(&SHA256, bytes).as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+        let signature = key_pair.sign(signed_payload("1.2.0", &current_target(), &sha256).as_bytes());
+        let asset = ReleaseAsset {
+            target: current_target(),
+            url: "https://example.invalid/maowbot".to_string(),
+            sha256,
+            signature: BASE64.encode(signature.as_ref()),
+        };
+        (BASE64.encode(key_pair.public_key().as_ref()), asset)
+    }
+
+    #[test]
+    fn test_verify_accepts_a_valid_signature() {
+        let (public_key, asset) = signed_release(b"the release");
+        verify_release(b"the release", "1.2.0", &asset, &public_key).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let (public_key, asset) = signed_release(b"the release");
+        let rejected = |version: &str, asset: &ReleaseAsset| {
+            verify_release(b"the release", version, asset, &public_key).unwrap_err().to_string()
+        };
+
+        // A flipped signature byte
+        let mut signature = BASE64.decode(&asset.signature).unwrap();
+        signature[0] ^= 1;
+        let tampered = ReleaseAsset { signature: BASE64.encode(&signature), ..asset.clone() };
+        assert!(rejected("1.2.0", &tampered).contains("signature does not match"));
+
+        // The same signed build offered as a newer release, or for another target
+        assert!(rejected("9.9.9", &asset).contains("signature does not match"));
+        let other_target = ReleaseAsset { target: "sparc-plan9".to_string(), ..asset.clone() };
+        assert!(rejected("1.2.0", &other_target).contains("signature does not match"));
+
+        // A different key
+        let (other_key, _) = signed_release(b"the release");
+        assert!(verify_release(b"the release", "1.2.0", &asset, &other_key).is_err());
+    }
+
+    #[test]
+    fn test_require_https() {
+        assert!(require_https("https://example.com/feed.json", "feed").is_ok());
+        assert!(require_https("http://example.com/feed.json", "feed").is_err());
+        assert!(require_https("not a url", "feed").is_err());
+    }
+}
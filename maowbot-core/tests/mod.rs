@@ -1,3 +1,7 @@
 // Many tests are temporarily disabled - they need significant refactoring
 // pub mod integration;
 //pub mod unit;
+
+// The chat cache tests are up to date with the cache API, so they run on their own
+#[path = "unit/cache_tests.rs"]
+mod cache_tests;
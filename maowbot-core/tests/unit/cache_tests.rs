@@ -1,14 +1,12 @@
 // File: maowbot-core/tests/unit/cache_tests.rs
 
 use std::collections::HashMap;
-use std::sync::Arc;
 use async_trait::async_trait;
 use chrono::{Utc, Duration};
 use uuid::Uuid;
 use maowbot_core::Error;
-use maowbot_core::cache::{
-    ChatCache, CacheConfig, TrimPolicy, CachedMessage
-};
+use maowbot_core::cache::message_cache::ChatCache;
+use maowbot_common::models::cache::{channel_key, CacheConfig, CachedMessage, ChannelRetention, TrimPolicy};
 use maowbot_common::models::user_analysis::UserAnalysis;
 use maowbot_common::traits::repository_traits::UserAnalysisRepository;
 
@@ -43,6 +41,10 @@ impl UserAnalysisRepository for MockUserAnalysisRepo {
         me.data.insert(user_id, cloned);
         Ok(())
     }
+
+    async fn insert_history(&self, _year_month: &str, _scores: &UserAnalysis) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 /// Helper to build a default ChatCache with the given policy overrides.
@@ -52,7 +54,7 @@ fn build_cache(
     let analysis_repo = MockUserAnalysisRepo::default();
     ChatCache::new(
         analysis_repo,
-        CacheConfig { trim_policy: policy },
+        CacheConfig::new(policy),
     )
 }
 
@@ -62,6 +64,7 @@ async fn test_add_and_retrieve_messages() -> Result<(), Error> {
         max_age_seconds: None,
         spam_score_cutoff: None,
         max_total_messages: Some(100),
+        max_messages_per_channel: None,
         max_messages_per_user: None,
         min_quality_score: None,
     };
@@ -77,8 +80,9 @@ async fn test_add_and_retrieve_messages() -> Result<(), Error> {
         text: "Hello from user1".into(),
         timestamp: now,
         token_count: 3,
+        user_roles: Vec::new(),
     };
-    cache.add_message(msg1.clone()).await;
+    cache.add_message(msg1.clone());
 
     let user2_id = Uuid::new_v4(); // Generate proper UUID
     let msg2 = CachedMessage {
@@ -89,12 +93,13 @@ async fn test_add_and_retrieve_messages() -> Result<(), Error> {
         text: "user2 checking in".into(),
         timestamp: now + Duration::seconds(5),
         token_count: 4,
+        user_roles: Vec::new(),
     };
-    cache.add_message(msg2.clone()).await;
+    cache.add_message(msg2.clone());
 
     // Retrieve all messages since 1 hour ago
     let since = now - Duration::hours(1);
-    let retrieved = cache.get_recent_messages(since, None, None).await;
+    let retrieved = cache.get_recent_messages(since, None, None);
     assert_eq!(retrieved.len(), 2, "Should retrieve both messages");
     // By default, messages come out in ascending order by timestamp
     assert_eq!(retrieved[0].text, "Hello from user1");
@@ -108,7 +113,8 @@ async fn test_ring_overwrites_when_full() -> Result<(), Error> {
     let policy = TrimPolicy {
         max_age_seconds: None,
         spam_score_cutoff: None,
-        max_total_messages: Some(2), // ring capacity = 2
+        max_total_messages: None,
+        max_messages_per_channel: Some(2), // ring capacity = 2
         max_messages_per_user: None,
         min_quality_score: None,
     };
@@ -123,6 +129,7 @@ async fn test_ring_overwrites_when_full() -> Result<(), Error> {
         text: "first".into(),
         timestamp: now,
         token_count: 1,
+        user_roles: Vec::new(),
     };
     let m2 = CachedMessage {
         platform: "test".into(),
@@ -132,6 +139,7 @@ async fn test_ring_overwrites_when_full() -> Result<(), Error> {
         text: "second".into(),
         timestamp: now + Duration::seconds(5),
         token_count: 1,
+        user_roles: Vec::new(),
     };
     let m3 = CachedMessage {
         platform: "test".into(),
@@ -141,15 +149,16 @@ async fn test_ring_overwrites_when_full() -> Result<(), Error> {
         text: "third".into(),
         timestamp: now + Duration::seconds(10),
         token_count: 1,
+        user_roles: Vec::new(),
     };
 
-    cache.add_message(m1.clone()).await;
-    cache.add_message(m2.clone()).await;
-    cache.add_message(m3.clone()).await;
+    cache.add_message(m1.clone());
+    cache.add_message(m2.clone());
+    cache.add_message(m3.clone());
 
     // Now only m2 and m3 should remain in the ring
     let since = now - Duration::hours(1);
-    let all = cache.get_recent_messages(since, None, None).await;
+    let all = cache.get_recent_messages(since, None, None);
     assert_eq!(all.len(), 2, "Capacity is 2, so it overwrote the oldest");
     assert_eq!(all[0].text, "second");
     assert_eq!(all[1].text, "third");
@@ -163,6 +172,7 @@ async fn test_per_user_capacity() -> Result<(), Error> {
         max_age_seconds: None,
         spam_score_cutoff: None,
         max_total_messages: Some(10),
+        max_messages_per_channel: None,
         max_messages_per_user: Some(2),
         min_quality_score: None,
     };
@@ -181,13 +191,14 @@ async fn test_per_user_capacity() -> Result<(), Error> {
             text: format!("msg #{}", i),
             timestamp: now + Duration::seconds(i as i64),
             token_count: 1,
+            user_roles: Vec::new(),
         };
-        cache.add_message(msg).await;
+        cache.add_message(msg);
     }
 
     // Because max_messages_per_user=2, only the last 2 for "abc" remain in the user's queue
     let since = now - Duration::minutes(1);
-    let retrieved_abc = cache.get_recent_messages(since, None, Some("abc")).await;
+    let retrieved_abc = cache.get_recent_messages(since, None, Some("abc"));
     assert_eq!(retrieved_abc.len(), 2);
     assert_eq!(retrieved_abc[0].text, "msg #1");
     assert_eq!(retrieved_abc[1].text, "msg #2");
@@ -195,7 +206,7 @@ async fn test_per_user_capacity() -> Result<(), Error> {
     // If we retrieve all messages (no filter_user_id), we might still see 3 in the ring
     // *except* that the first message's index was ejected from user "abc"'s queue.
     // However, the ring doesn't forcibly remove it unless capacity is exceeded or it's old.
-    let all = cache.get_recent_messages(since, None, None).await;
+    let all = cache.get_recent_messages(since, None, None);
     assert_eq!(all.len(), 3, "All remain in ring for now, but user queue is trimmed to 2");
 
     Ok(())
@@ -207,6 +218,7 @@ async fn test_age_based_trimming_on_add() -> Result<(), Error> {
         max_age_seconds: Some(3600), // 1 hour
         spam_score_cutoff: None,
         max_total_messages: Some(100),
+        max_messages_per_channel: None,
         max_messages_per_user: None,
         min_quality_score: None,
     };
@@ -221,9 +233,10 @@ async fn test_age_based_trimming_on_add() -> Result<(), Error> {
         text: "too old".into(),
         timestamp: now - Duration::hours(2),
         token_count: 1,
+        user_roles: Vec::new(),
     };
     // Adding an old message will cause the inline age-based trim to remove it immediately.
-    cache.add_message(old_msg.clone()).await;
+    cache.add_message(old_msg.clone());
 
     let new_msg = CachedMessage {
         platform: "test".into(),
@@ -233,11 +246,12 @@ async fn test_age_based_trimming_on_add() -> Result<(), Error> {
         text: "newer message".into(),
         timestamp: now,
         token_count: 1,
+        user_roles: Vec::new(),
     };
-    cache.add_message(new_msg.clone()).await;
+    cache.add_message(new_msg.clone());
 
     let since = now - Duration::hours(3);
-    let messages = cache.get_recent_messages(since, None, None).await;
+    let messages = cache.get_recent_messages(since, None, None);
     assert_eq!(messages.len(), 1);
     assert_eq!(messages[0].text, "newer message");
 
@@ -250,6 +264,7 @@ async fn test_token_limit_stops_early() -> Result<(), Error> {
         max_age_seconds: None,
         spam_score_cutoff: None,
         max_total_messages: Some(10),
+        max_messages_per_channel: None,
         max_messages_per_user: None,
         min_quality_score: None,
     };
@@ -264,6 +279,7 @@ async fn test_token_limit_stops_early() -> Result<(), Error> {
         text: "one".into(),
         timestamp: now,
         token_count: 4,
+        user_roles: Vec::new(),
     };
     let m2 = CachedMessage {
         platform: "test".into(),
@@ -273,6 +289,7 @@ async fn test_token_limit_stops_early() -> Result<(), Error> {
         text: "two".into(),
         timestamp: now + Duration::seconds(10),
         token_count: 3,
+        user_roles: Vec::new(),
     };
     let m3 = CachedMessage {
         platform: "test".into(),
@@ -282,11 +299,12 @@ async fn test_token_limit_stops_early() -> Result<(), Error> {
         text: "three".into(),
         timestamp: now + Duration::seconds(20),
         token_count: 5,
+        user_roles: Vec::new(),
     };
 
-    cache.add_message(m1).await;
-    cache.add_message(m2).await;
-    cache.add_message(m3).await;
+    cache.add_message(m1);
+    cache.add_message(m2);
+    cache.add_message(m3);
 
     let since = now - Duration::hours(1);
 
@@ -296,7 +314,7 @@ async fn test_token_limit_stops_early() -> Result<(), Error> {
     //   (1) "one" (4 tokens so far),
     //   (2) "two" (4+3=7 tokens),
     //   (3) next is "three" which has 5 tokens => total would be 12, so we stop.
-    let retrieved = cache.get_recent_messages(since, Some(7), None).await;
+    let retrieved = cache.get_recent_messages(since, Some(7), None);
     assert_eq!(retrieved.len(), 2);
     assert_eq!(retrieved[0].text, "one");
     assert_eq!(retrieved[1].text, "two");
@@ -318,20 +336,22 @@ async fn test_trim_spammy_users() -> Result<(), Error> {
         horni_score: 0.2,
         ai_notes: None,
         moderator_notes: None,
+        bot_score: 0.0,
+        bot_signals: Vec::new(),
+        bot_scored_at: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
     repo.data.insert(spammy_id, spam_user);
 
-    let config = CacheConfig {
-        trim_policy: TrimPolicy {
-            max_age_seconds: None,
-            spam_score_cutoff: Some(5.0),
-            max_total_messages: Some(100),
-            max_messages_per_user: None,
-            min_quality_score: None,
-        },
-    };
+    let config = CacheConfig::new(TrimPolicy {
+        max_age_seconds: None,
+        spam_score_cutoff: Some(5.0),
+        max_total_messages: Some(100),
+        max_messages_per_channel: None,
+        max_messages_per_user: None,
+        min_quality_score: None,
+    });
     let cache = ChatCache::new(repo.clone(), config);
 
     let now = Utc::now();
@@ -343,8 +363,9 @@ async fn test_trim_spammy_users() -> Result<(), Error> {
         text: "buy followers cheap!!!!".into(),
         timestamp: now,
         token_count: 2,
+        user_roles: Vec::new(),
     };
-    cache.add_message(spam_msg.clone()).await;
+    cache.add_message(spam_msg.clone());
 
     let normal_msg = CachedMessage {
         platform: "test".into(),
@@ -354,25 +375,81 @@ async fn test_trim_spammy_users() -> Result<(), Error> {
         text: "normal user message".into(),
         timestamp: now + Duration::seconds(1),
         token_count: 2,
+        user_roles: Vec::new(),
     };
-    cache.add_message(normal_msg.clone()).await;
+    cache.add_message(normal_msg.clone());
 
     // Before trim
     let since = now - Duration::hours(1);
-    let all_pre = cache.get_recent_messages(since, None, None).await;
+    let all_pre = cache.get_recent_messages(since, None, None);
     assert_eq!(all_pre.len(), 2);
 
     // Now trim spammy users
     cache.trim_spammy_users().await;
 
     // The "spammy" user should be purged from user_map, and also removed from ring
-    let all_post = cache.get_recent_messages(since, None, None).await;
+    let all_post = cache.get_recent_messages(since, None, None);
     assert_eq!(all_post.len(), 1, "spammy messages removed");
     assert_eq!(all_post[0].text, "normal user message");
 
     // Confirm user_id=spammy is no longer in the ring or user queues
-    let spam_only = cache.get_recent_messages(since, None, Some("spammy")).await;
+    let spam_only = cache.get_recent_messages(since, None, Some("spammy"));
     assert!(spam_only.is_empty());
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_per_channel_retention() -> Result<(), Error> {
+    let policy = TrimPolicy {
+        max_age_seconds: None,
+        spam_score_cutoff: None,
+        max_total_messages: None,
+        max_messages_per_channel: Some(10),
+        max_messages_per_user: None,
+        min_quality_score: None,
+    };
+    let mut config = CacheConfig::new(policy);
+    // This channel only keeps the last 10 minutes
+    config.channel_retention.insert(
+        channel_key("twitch-irc", "#short"),
+        ChannelRetention { max_messages: None, max_age_seconds: Some(600) },
+    );
+    let cache = ChatCache::new(MockUserAnalysisRepo::default(), config);
+
+    let now = Utc::now();
+    let message = |channel: &str, text: &str, age_minutes: i64| CachedMessage {
+        platform: "twitch-irc".into(),
+        channel: channel.into(),
+        user_id: Uuid::new_v4(),
+        user_name: "viewer".into(),
+        text: text.into(),
+        timestamp: now - Duration::minutes(age_minutes),
+        token_count: 1,
+        user_roles: Vec::new(),
+    };
+    cache.add_message(message("#short", "old", 30));
+    cache.add_message(message("#short", "new", 1));
+    cache.add_message(message("#long", "old", 30));
+    cache.add_message(message("#long", "new", 1));
+
+    let since = now - Duration::hours(1);
+    let short: Vec<_> = cache.get_channel_messages("twitch-irc", "#short", since, None)
+        .into_iter().map(|m| m.text).collect();
+    assert_eq!(short, ["new"], "messages past the channel's max age are dropped");
+    assert_eq!(cache.get_channel_messages("twitch-irc", "#long", since, None).len(), 2);
+
+    // Limiting another channel at runtime trims it right away
+    cache.set_channel_retention("twitch-irc", "#long", ChannelRetention { max_messages: Some(1), max_age_seconds: None });
+    let long: Vec<_> = cache.get_channel_messages("twitch-irc", "#long", since, None)
+        .into_iter().map(|m| m.text).collect();
+    assert_eq!(long, ["new"]);
+    assert_eq!(cache.len(), 2);
+
+    // Cleared: back to the policy's 10 messages and no age limit
+    cache.clear_channel_retention("twitch-irc", "#short");
+    cache.add_message(message("#short", "older again", 30));
+    assert_eq!(cache.get_channel_messages("twitch-irc", "#short", since, None).len(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! The "Approvals" tab: channel point redemptions waiting for the streamer,
+//! with buttons to approve or deny (and refund) each one. The queue reloads
+//! every few seconds while the tab is open.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use egui::{Color32, RichText, ScrollArea};
+use maowbot_common_ui::GrpcClient;
+use maowbot_common_ui::commands::redeem::RedeemCommands;
+use maowbot_proto::maowbot::services::RedeemApproval;
+
+const RELOAD_EVERY: Duration = Duration::from_secs(5);
+
+/// What the last request left behind.
+#[derive(Default)]
+struct ApprovalsView {
+    pending: Vec<RedeemApproval>,
+    loaded_at: Option<Instant>,
+    busy: bool,
+    /// The last error, or what the last decision did
+    status: Option<(bool, String)>,
+}
+
+enum Request {
+    Load,
+    Approve(String),
+    Deny(String),
+}
+
+pub struct ApprovalsPanel {
+    url: String,
+    runtime: tokio::runtime::Handle,
+    view: Arc<Mutex<ApprovalsView>>,
+}
+
+impl ApprovalsPanel {
+    /// Must be created inside the tokio runtime.
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            runtime: tokio::runtime::Handle::current(),
+            view: Arc::new(Mutex::new(ApprovalsView::default())),
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui) {
+        let needs_load = {
+            let view = self.view.lock().unwrap();
+            !view.busy && view.loaded_at.is_none_or(|at| at.elapsed() >= RELOAD_EVERY)
+        };
+        if needs_load {
+            self.send(ui.ctx(), Request::Load);
+        }
+        ui.ctx().request_repaint_after(RELOAD_EVERY);
+
+        ui.horizontal(|ui| {
+            ui.heading("Redeem Approvals");
+            if self.view.lock().unwrap().busy {
+                ui.spinner();
+            }
+        });
+        if let Some((ok, message)) = self.view.lock().unwrap().status.clone() {
+            let color = if ok { Color32::from_rgb(0, 200, 0) } else { Color32::from_rgb(255, 100, 100) };
+            ui.colored_label(color, message);
+        }
+        ui.separator();
+
+        let pending = self.view.lock().unwrap().pending.clone();
+        if pending.is_empty() {
+            ui.label("Nothing waiting for approval.");
+            return;
+        }
+        let now = chrono::Utc::now().timestamp();
+        ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            egui::Grid::new("redeem_approvals").striped(true).num_columns(5).show(ui, |ui| {
+                ui.label(RichText::new("Reward").strong());
+                ui.label(RichText::new("Viewer").strong());
+                ui.label(RichText::new("Input").strong());
+                ui.label(RichText::new("Waiting").strong());
+                ui.label("");
+                ui.end_row();
+
+                for approval in &pending {
+                    ui.label(&approval.reward_name);
+                    ui.label(&approval.user_name);
+                    ui.label(&approval.user_input);
+                    let waited = approval.requested_at.as_ref().map(|t| now - t.seconds).unwrap_or_default();
+                    ui.label(format!("{}:{:02}", waited / 60, waited % 60));
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Approve").clicked() {
+                            self.send(ui.ctx(), Request::Approve(approval.approval_id.clone()));
+                        }
+                        if ui.small_button("Deny").on_hover_text("Refunds the points").clicked() {
+                            self.send(ui.ctx(), Request::Deny(approval.approval_id.clone()));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn send(&self, ctx: &egui::Context, request: Request) {
+        let url = self.url.clone();
+        let view = self.view.clone();
+        let ctx = ctx.clone();
+        view.lock().unwrap().busy = true;
+
+        self.runtime.spawn(async move {
+            let result = decide_and_load(&url, request).await;
+            {
+                let mut view = view.lock().unwrap();
+                view.busy = false;
+                view.loaded_at = Some(Instant::now());
+                match result {
+                    Ok((pending, done)) => {
+                        view.pending = pending;
+                        if let Some(done) = done {
+                            view.status = Some((true, done));
+                        }
+                    }
+                    Err(e) => view.status = Some((false, e)),
+                }
+            }
+            ctx.request_repaint();
+        });
+    }
+}
+
+/// Makes the decision, if any, and fetches the queue as it is afterwards.
+async fn decide_and_load(url: &str, request: Request) -> Result<(Vec<RedeemApproval>, Option<String>), String> {
+    let client = GrpcClient::connect(url).await.map_err(|e| format!("Can't reach the bot: {}", e))?;
+    let done = match request {
+        Request::Load => None,
+        Request::Approve(id) => {
+            let a = RedeemCommands::approve(&client, &id).await.map_err(|e| format!("Approving failed: {}", e))?;
+            Some(format!("Approved '{}' from {}", a.reward_name, a.user_name))
+        }
+        Request::Deny(id) => {
+            let a = RedeemCommands::deny(&client, &id).await.map_err(|e| format!("Denying failed: {}", e))?;
+            Some(format!("Denied '{}' from {}; points refunded", a.reward_name, a.user_name))
+        }
+    };
+    let pending = RedeemCommands::list_approvals(&client).await
+        .map_err(|e| format!("Loading the queue failed: {}", e))?;
+    Ok((pending, done))
+}
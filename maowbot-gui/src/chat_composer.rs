@@ -0,0 +1,268 @@
+//! The main chat input, with a suggestion popup for `@` mentions, `:` emote
+//! shortcodes and `!` commands from the shared completion engine. Up/Down move
+//! through suggestions, Tab or Enter takes one and Escape closes the popup.
+
+use std::sync::{Arc, Mutex, RwLock};
+use egui::text::CCursor;
+use egui::text_edit::TextEditState;
+use egui::text_selection::CCursorRange;
+use egui::{Key, Modifiers, RichText, ScrollArea, TextEdit};
+use maowbot_common_ui::{ChatEvent, GrpcClient};
+use maowbot_common_ui::completion::{
+    CompletionConfig, CompletionContext, CompletionEngine, CompletionEngineBuilder, CompletionItem, CompletionScope,
+};
+use maowbot_common_ui::completion::providers::{
+    ChatHistory, ChatHistoryCompletionProvider, CommandCompletionProvider, EmoteCompletionProvider,
+};
+
+const POPUP_MAX_HEIGHT: f32 = 220.0;
+
+/// Suggestions and the input text and cursor they were looked up for.
+#[derive(Default)]
+struct Suggestions {
+    input: String,
+    cursor: usize,
+    items: Vec<CompletionItem>,
+}
+
+pub struct ChatComposer {
+    text: String,
+    /// Byte offset of the cursor in `text` as of last frame
+    cursor: usize,
+    history: Arc<RwLock<ChatHistory>>,
+    config: CompletionConfig,
+    /// Chat history only until the bot is reachable, then commands and emotes too
+    engine: Arc<Mutex<Arc<CompletionEngine>>>,
+    /// None outside the tokio runtime, which turns suggestions off
+    runtime: Option<tokio::runtime::Handle>,
+    /// Channel of the latest message, for the completion scope
+    channel: String,
+    suggestions: Arc<Mutex<Suggestions>>,
+    /// Text and cursor last looked up, to notice edits
+    requested: (String, usize),
+    highlight: usize,
+    /// Closed with Escape; opens again on the next edit
+    dismissed: bool,
+    popup_hovered: bool,
+}
+
+impl ChatComposer {
+    pub fn new() -> Self {
+        let history = Arc::new(RwLock::new(ChatHistory::new()));
+        let config = CompletionConfig::default();
+        let engine = CompletionEngineBuilder::new()
+            .with_config(config.clone())
+            .with_provider(Box::new(ChatHistoryCompletionProvider::new(history.clone())))
+            .build();
+
+        Self {
+            text: String::new(),
+            cursor: 0,
+            history,
+            config,
+            engine: Arc::new(Mutex::new(Arc::new(engine))),
+            runtime: tokio::runtime::Handle::try_current().ok(),
+            channel: String::new(),
+            suggestions: Arc::new(Mutex::new(Suggestions::default())),
+            requested: (String::new(), 0),
+            highlight: 0,
+            dismissed: false,
+            popup_hovered: false,
+        }
+    }
+
+    /// Adds command and emote suggestions once the bot at `url` answers.
+    pub fn connect(&self, url: String) {
+        let Some(runtime) = &self.runtime else { return };
+        let history = self.history.clone();
+        let config = self.config.clone();
+        let engine = self.engine.clone();
+
+        runtime.spawn(async move {
+            let client = match GrpcClient::connect(&url).await {
+                Ok(client) => Arc::new(client),
+                Err(e) => {
+                    tracing::warn!("Chat input suggests chat history only, can't reach the bot: {}", e);
+                    return;
+                }
+            };
+            let full = CompletionEngineBuilder::new()
+                .with_config(config)
+                .with_provider(Box::new(ChatHistoryCompletionProvider::new(history)))
+                .with_provider(Box::new(CommandCompletionProvider::new(client.clone())))
+                .with_provider(Box::new(EmoteCompletionProvider::new(client)))
+                .build();
+            *engine.lock().unwrap() = Arc::new(full);
+        });
+    }
+
+    pub fn record(&mut self, event: &ChatEvent) {
+        self.history.write().unwrap().record(event);
+        self.channel.clone_from(&event.channel);
+    }
+
+    /// The typed message, leaving the input empty; None if there's nothing to send.
+    pub fn take_message(&mut self) -> Option<String> {
+        let text = std::mem::take(&mut self.text);
+        self.cursor = 0;
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    /// Draws the input and its suggestions. Returns true when Enter was
+    /// pressed to send, rather than to take a suggestion.
+    pub fn show(&mut self, ui: &mut egui::Ui, width: f32) -> bool {
+        let id = ui.make_persistent_id("chat_composer");
+        let has_focus = ui.memory(|m| m.has_focus(id));
+
+        // Take the keys the popup uses before the text field sees them
+        let items = self.current_items();
+        let mut scroll_to_highlight = false;
+        if has_focus && !items.is_empty() && !self.dismissed {
+            let (down, up, accept, escape) = ui.input_mut(|i| (
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::Tab) || i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            ));
+            if down || up {
+                let count = items.len();
+                self.highlight = if down { (self.highlight + 1) % count } else { (self.highlight + count - 1) % count };
+                scroll_to_highlight = true;
+            }
+            if accept {
+                self.accept(ui.ctx(), id, &items[self.highlight.min(items.len() - 1)]);
+            }
+            if escape {
+                self.dismissed = true;
+            }
+        }
+
+        let output = TextEdit::singleline(&mut self.text)
+            .id(id)
+            .desired_width(width)
+            .hint_text("Type a message...")
+            .lock_focus(true)
+            .show(ui);
+        if let Some(range) = output.cursor_range {
+            let chars = range.primary.ccursor.index;
+            self.cursor = self.text.char_indices().nth(chars).map_or(self.text.len(), |(i, _)| i);
+        }
+
+        if (self.text.as_str(), self.cursor) != (self.requested.0.as_str(), self.requested.1) {
+            self.requested = (self.text.clone(), self.cursor);
+            self.highlight = 0;
+            self.dismissed = false;
+            self.request(ui.ctx());
+        }
+
+        // Stay open while the pointer is on the popup, as clicking it takes
+        // focus from the text field before the click lands
+        let items = self.current_items();
+        let open = !items.is_empty() && !self.dismissed && (has_focus || self.popup_hovered);
+        self.popup_hovered = false;
+        if open {
+            self.show_popup(ui, id, output.response.rect, &items, scroll_to_highlight);
+        }
+
+        let send = output.response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+        if send {
+            ui.memory_mut(|m| m.request_focus(id));
+        }
+        send
+    }
+
+    fn show_popup(&mut self, ui: &egui::Ui, id: egui::Id, input_rect: egui::Rect, items: &[CompletionItem], scroll: bool) {
+        let mut picked = None;
+        let area = egui::Area::new(id.with("suggestions"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::LEFT_BOTTOM)
+            .fixed_pos(input_rect.left_top())
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_min_width(input_rect.width());
+                    ScrollArea::vertical().max_height(POPUP_MAX_HEIGHT).show(ui, |ui| {
+                        for (i, item) in items.iter().enumerate() {
+                            let mut label = item.display.clone();
+                            if self.config.show_icons {
+                                if let Some(icon) = &item.icon {
+                                    label = format!("{} {}", icon, label);
+                                }
+                            }
+                            ui.horizontal(|ui| {
+                                let response = ui.selectable_label(i == self.highlight, label);
+                                if response.clicked() {
+                                    picked = Some(i);
+                                }
+                                if scroll && i == self.highlight {
+                                    response.scroll_to_me(None);
+                                }
+                                if self.config.show_descriptions {
+                                    if let Some(description) = &item.description {
+                                        ui.label(RichText::new(description).weak());
+                                    }
+                                }
+                            });
+                        }
+                    });
+                });
+            });
+        self.popup_hovered = area.response.contains_pointer();
+
+        if let Some(i) = picked {
+            self.accept(ui.ctx(), id, &items[i]);
+        }
+    }
+
+    /// Suggestions for the text and cursor as they are now.
+    fn current_items(&self) -> Vec<CompletionItem> {
+        let suggestions = self.suggestions.lock().unwrap();
+        if suggestions.input == self.text && suggestions.cursor == self.cursor {
+            suggestions.items.clone()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn context(&self) -> CompletionContext {
+        let scope = CompletionScope::TwitchChat { channel: self.channel.clone() };
+        CompletionContext::new(scope, self.text.clone(), self.cursor)
+    }
+
+    fn request(&self, ctx: &egui::Context) {
+        let context = self.context();
+        // Commands only complete as the first word
+        let wants = context.is_mention()
+            || context.is_emote_shortcode()
+            || (context.current_word().starts_with('!') && context.previous_words().is_empty());
+        let Some(runtime) = self.runtime.as_ref().filter(|_| wants) else {
+            *self.suggestions.lock().unwrap() = Suggestions::default();
+            return;
+        };
+
+        let engine = self.engine.lock().unwrap().clone();
+        let suggestions = self.suggestions.clone();
+        let ctx = ctx.clone();
+        runtime.spawn(async move {
+            let items = engine.get_completions(&context).await;
+            *suggestions.lock().unwrap() = Suggestions {
+                input: context.input,
+                cursor: context.cursor_position,
+                items,
+            };
+            ctx.request_repaint();
+        });
+    }
+
+    fn accept(&mut self, ctx: &egui::Context, id: egui::Id, item: &CompletionItem) {
+        let (text, cursor) = self.context().replace_current_word(&item.replacement);
+        self.text = text;
+        self.cursor = cursor;
+
+        let chars = self.text[..cursor].chars().count();
+        let mut state = TextEditState::load(ctx, id).unwrap_or_default();
+        state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(chars))));
+        state.store(ctx, id);
+        ctx.memory_mut(|m| m.request_focus(id));
+    }
+}
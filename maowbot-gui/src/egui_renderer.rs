@@ -1,39 +1,47 @@
 use crossbeam_channel::Sender;
 use egui::{Color32, RichText, ScrollArea, TextEdit, Vec2, Rect};
-use maowbot_common_ui::{AppState, UIEvent, LayoutSection, ProcessManager, ProcessType};
+use maowbot_common_ui::{AppState, ConnectionStatus, UIEvent, LayoutSection, ProcessManager, ProcessType};
 use maowbot_common_ui::events::ChatCommand;
 use std::sync::{Arc, Mutex};
 
+use crate::approvals_panel::ApprovalsPanel;
+use crate::chat_composer::ChatComposer;
 use crate::layout_constants::*;
+use crate::schedule_panel::SchedulePanel;
 use crate::settings::Settings;
 use crate::WindowMode;
 
 pub struct EguiRenderer {
-    input_buffer: String,
+    composer: ChatComposer,
     secondary_input_buffer: String,
-    show_settings: bool,
     window_mode: WindowMode,
     settings: Arc<Mutex<Settings>>,
+    /// Shared with the secondary window; None when not connected to a server
+    schedule: Option<Arc<Mutex<SchedulePanel>>>,
+    /// Shared like `schedule`
+    approvals: Option<Arc<Mutex<ApprovalsPanel>>>,
 }
 
 impl EguiRenderer {
     pub fn new(window_mode: WindowMode) -> Self {
         Self {
-            input_buffer: String::new(),
+            composer: ChatComposer::new(),
             secondary_input_buffer: String::new(),
-            show_settings: false,
             window_mode,
             settings: Arc::new(Mutex::new(Settings::new())),
+            schedule: None,
+            approvals: None,
         }
     }
     
     pub fn new_with_settings(window_mode: WindowMode, settings: Arc<Mutex<Settings>>) -> Self {
         Self {
-            input_buffer: String::new(),
+            composer: ChatComposer::new(),
             secondary_input_buffer: String::new(),
-            show_settings: false,
             window_mode,
             settings,
+            schedule: None,
+            approvals: None,
         }
     }
     
@@ -41,6 +49,56 @@ impl EguiRenderer {
         self.settings.clone()
     }
 
+    pub fn chat_composer(&mut self) -> &mut ChatComposer {
+        &mut self.composer
+    }
+
+    pub fn set_schedule_panel(&mut self, panel: SchedulePanel) {
+        self.schedule = Some(Arc::new(Mutex::new(panel)));
+    }
+
+    pub fn schedule_panel(&self) -> Option<Arc<Mutex<SchedulePanel>>> {
+        self.schedule.clone()
+    }
+
+    pub fn share_schedule_panel(&mut self, panel: Option<Arc<Mutex<SchedulePanel>>>) {
+        self.schedule = panel;
+    }
+
+    pub fn set_approvals_panel(&mut self, panel: ApprovalsPanel) {
+        self.approvals = Some(Arc::new(Mutex::new(panel)));
+    }
+
+    pub fn approvals_panel(&self) -> Option<Arc<Mutex<ApprovalsPanel>>> {
+        self.approvals.clone()
+    }
+
+    pub fn share_approvals_panel(&mut self, panel: Option<Arc<Mutex<ApprovalsPanel>>>) {
+        self.approvals = panel;
+    }
+
+    /// The latest crash or ops alert, first line shown, the rest on hover.
+    fn render_process_notice(ui: &mut egui::Ui, state: &AppState) {
+        let notice = state.process_notice.lock().unwrap().clone();
+        let Some(notice) = notice else { return };
+        ui.separator();
+        let summary = notice.lines().next().unwrap_or_default();
+        ui.colored_label(Color32::from_rgb(255, 160, 0), summary).on_hover_text(&notice);
+        if ui.small_button("✕").clicked() {
+            *state.process_notice.lock().unwrap() = None;
+        }
+    }
+
+    /// Status dot for the bot connection; hovering shows why it's down.
+    fn render_grpc_status(ui: &mut egui::Ui, status: &ConnectionStatus) {
+        let color = match status {
+            ConnectionStatus::Connected => Color32::from_rgb(0, 255, 0),
+            ConnectionStatus::Connecting { .. } => Color32::from_rgb(255, 200, 0),
+            ConnectionStatus::Disconnected { .. } => Color32::from_rgb(255, 0, 0),
+        };
+        ui.colored_label(color, "●").on_hover_text(status.to_string());
+    }
+
     pub fn handle_ui_event(
         &mut self,
         ctx: &egui::Context,
@@ -230,15 +288,11 @@ impl EguiRenderer {
                 ui.separator();
 
                 // Status indicators
-                let grpc_connected = *state.grpc_connected.lock().unwrap();
+                let grpc_status = state.grpc_status.lock().unwrap().clone();
                 let overlay_running = *state.overlay_running.lock().unwrap();
 
                 ui.label("gRPC:");
-                if grpc_connected {
-                    ui.colored_label(Color32::from_rgb(0, 255, 0), "●");
-                } else {
-                    ui.colored_label(Color32::from_rgb(255, 0, 0), "●");
-                }
+                Self::render_grpc_status(ui, &grpc_status);
 
                 ui.separator();
 
@@ -249,6 +303,8 @@ impl EguiRenderer {
                     ui.colored_label(Color32::from_rgb(255, 0, 0), "●");
                 }
 
+                Self::render_process_notice(ui, state);
+
                 ui.separator();
 
                 // Control buttons
@@ -257,7 +313,7 @@ impl EguiRenderer {
                         let pm = process_manager.clone();
                         tokio::task::spawn_blocking(move || {
                             tokio::runtime::Handle::current().block_on(async move {
-                                let manager = pm.lock().unwrap();
+                                let manager = pm.lock().unwrap().clone();
                                 if let Err(e) = manager.stop(ProcessType::Overlay).await {
                                     tracing::error!("Failed to stop overlay: {}", e);
                                 }
@@ -268,7 +324,7 @@ impl EguiRenderer {
                         let pm = process_manager.clone();
                         tokio::task::spawn_blocking(move || {
                             tokio::runtime::Handle::current().block_on(async move {
-                                let manager = pm.lock().unwrap();
+                                let manager = pm.lock().unwrap().clone();
                                 // Stop then start
                                 let _ = manager.stop(ProcessType::Overlay).await;
                                 tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
@@ -283,7 +339,7 @@ impl EguiRenderer {
                         let pm = process_manager.clone();
                         tokio::task::spawn_blocking(move || {
                             tokio::runtime::Handle::current().block_on(async move {
-                                let manager = pm.lock().unwrap();
+                                let manager = pm.lock().unwrap().clone();
                                 if let Err(e) = manager.start_overlay().await {
                                     tracing::error!("Failed to start overlay: {}", e);
                                 }
@@ -479,7 +535,7 @@ impl EguiRenderer {
                                 child_rect.size() - Vec2::new(margin * 2.0, margin * 2.0)
                             );
                             
-                            ui.allocate_ui_at_rect(child_rect, |ui| {
+                            ui.allocate_new_ui(egui::UiBuilder::new().max_rect(child_rect), |ui| {
                                 match section {
                                     LayoutSection::LeftChat => self.render_left_chat(ui, state, command_tx),
                                     LayoutSection::TabArea => self.render_tab_area(ui, state),
@@ -514,15 +570,11 @@ impl EguiRenderer {
                 ui.separator();
 
                 // Status indicators
-                let grpc_connected = *state.grpc_connected.lock().unwrap();
+                let grpc_status = state.grpc_status.lock().unwrap().clone();
                 let overlay_running = *state.overlay_running.lock().unwrap();
 
                 ui.label("gRPC:");
-                if grpc_connected {
-                    ui.colored_label(Color32::from_rgb(0, 255, 0), "●");
-                } else {
-                    ui.colored_label(Color32::from_rgb(255, 0, 0), "●");
-                }
+                Self::render_grpc_status(ui, &grpc_status);
 
                 ui.separator();
 
@@ -533,6 +585,8 @@ impl EguiRenderer {
                     ui.colored_label(Color32::from_rgb(255, 0, 0), "●");
                 }
 
+                Self::render_process_notice(ui, state);
+
                 ui.separator();
 
                 // Control buttons
@@ -541,7 +595,7 @@ impl EguiRenderer {
                         let pm = process_manager.clone();
                         tokio::task::spawn_blocking(move || {
                             tokio::runtime::Handle::current().block_on(async move {
-                                let manager = pm.lock().unwrap();
+                                let manager = pm.lock().unwrap().clone();
                                 if let Err(e) = manager.stop(ProcessType::Overlay).await {
                                     tracing::error!("Failed to stop overlay: {}", e);
                                 }
@@ -553,7 +607,7 @@ impl EguiRenderer {
                         let pm = process_manager.clone();
                         tokio::task::spawn_blocking(move || {
                             tokio::runtime::Handle::current().block_on(async move {
-                                let manager = pm.lock().unwrap();
+                                let manager = pm.lock().unwrap().clone();
                                 if let Err(e) = manager.start_overlay().await {
                                     tracing::error!("Failed to start overlay: {}", e);
                                 }
@@ -709,7 +763,7 @@ impl EguiRenderer {
             // Account for vertical container padding
             let chat_height = available_height - CHAT_CHROME_HEIGHT - VERTICAL_CONTAINER_PADDING;
             ScrollArea::vertical()
-                .id_source("secondary_chat_scroll")
+                .id_salt("secondary_chat_scroll")
                 .max_height(chat_height)
                 .auto_shrink([false; 2])
                 .stick_to_bottom(true)
@@ -783,6 +837,16 @@ impl EguiRenderer {
                 if ui.selectable_label(*active_tab == "Browser", "Browser").clicked() {
                     *active_tab = "Browser".to_string();
                 }
+                ui.separator();
+
+                if ui.selectable_label(*active_tab == "Schedule", "Schedule").clicked() {
+                    *active_tab = "Schedule".to_string();
+                }
+                ui.separator();
+
+                if ui.selectable_label(*active_tab == "Approvals", "Approvals").clicked() {
+                    *active_tab = "Approvals".to_string();
+                }
         });
         
         ui.separator();
@@ -813,6 +877,22 @@ impl EguiRenderer {
                     ui.label("Web Browser\n(CEF Embed Placeholder)");
                 });
             }
+            "Schedule" => match &self.schedule {
+                Some(panel) => panel.lock().unwrap().render(ui),
+                None => {
+                    ui.centered_and_justified(|ui| {
+                        ui.label("Stream Schedule\n(Not connected to the bot)");
+                    });
+                }
+            },
+            "Approvals" => match &self.approvals {
+                Some(panel) => panel.lock().unwrap().render(ui),
+                None => {
+                    ui.centered_and_justified(|ui| {
+                        ui.label("Redeem Approvals\n(Not connected to the bot)");
+                    });
+                }
+            },
             _ => {}
         }
     }
@@ -830,7 +910,7 @@ impl EguiRenderer {
             // Chat area - account for vertical container padding
             let chat_height = available_height - CHAT_CHROME_HEIGHT - VERTICAL_CONTAINER_PADDING;
             ScrollArea::vertical()
-                .id_source("main_chat_scroll")
+                .id_salt("main_chat_scroll")
                 .max_height(chat_height)
                 .auto_shrink([false; 2])
                 .stick_to_bottom(true)
@@ -854,28 +934,15 @@ impl EguiRenderer {
             
             // Input area
             ui.horizontal(|ui| {
-                let response = ui.add(
-                    TextEdit::singleline(&mut self.input_buffer)
-                        .desired_width(ui.available_width() - 60.0)
-                        .hint_text("Type a message...")
-                );
-                
-                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if !self.input_buffer.is_empty() {
-                        let _ = command_tx.send(ChatCommand::SendMessage(
-                            self.input_buffer.clone()
-                        ));
-                        self.input_buffer.clear();
-                        response.request_focus();
+                let width = ui.available_width() - 60.0;
+                let entered = self.composer.show(ui, width);
+                let clicked = ui.button("Send").clicked();
+
+                if entered || clicked {
+                    if let Some(message) = self.composer.take_message() {
+                        let _ = command_tx.send(ChatCommand::SendMessage(message));
                     }
                 }
-                
-                if ui.button("Send").clicked() && !self.input_buffer.is_empty() {
-                    let _ = command_tx.send(ChatCommand::SendMessage(
-                        self.input_buffer.clone()
-                    ));
-                    self.input_buffer.clear();
-                }
             });
         });
     }
@@ -992,7 +1059,7 @@ impl EguiRenderer {
             .show(ui, |ui| {
             let mut player_num = 1;
             for row in 0..rows {
-                for col in 0..cols {
+                for _col in 0..cols {
                     ui.allocate_ui(Vec2::new(cell_width, cell_height), |ui| {
                         self.render_video_player(
                             ui,
@@ -1,16 +1,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod approvals_panel;
+mod chat_composer;
 mod egui_renderer;
 mod layout_constants;
+mod schedule_panel;
 mod settings;
 
 use anyhow::Result;
 use crossbeam_channel::{bounded, Sender, Receiver};
 use eframe::egui;
-use maowbot_common_ui::{AppState, AppEvent, SharedGrpcClient, ProcessManager, ProcessType};
+use maowbot_common_ui::{AppState, AppEvent, SharedGrpcClient, ProcessManager, ProcessType, SettingsSync, SharedSettings};
+use maowbot_common_ui::settings::{AudioSettings, StreamOverlaySettings, UISettings};
 use maowbot_common_ui::events::ChatCommand;
 use std::sync::{Arc, Mutex};
-use tracing::{info, error};
+use tracing::error;
 use tracing_subscriber::EnvFilter;
 
 #[derive(Clone)]
@@ -30,6 +34,16 @@ struct DesktopApp {
     secondary_window_id: Option<egui::ViewportId>,
     should_open_secondary: bool,
     secondary_renderer: Option<egui_renderer::EguiRenderer>,
+    settings_sync: Option<SettingsSync>,
+    synced_settings: SyncedSettings,
+}
+
+/// The shared settings as last saved to or received from the server, to tell
+/// which ones the user has changed since.
+struct SyncedSettings {
+    ui: UISettings,
+    audio: AudioSettings,
+    overlay: StreamOverlaySettings,
 }
 
 impl DesktopApp {
@@ -49,12 +63,16 @@ impl DesktopApp {
         let (command_tx, command_rx) = bounded(100);
 
         // Use provided state or create new
-        let state = state.unwrap_or_else(AppState::new);
+        let state = state.unwrap_or_default();
 
         // Create process manager with event sender
         let process_manager = ProcessManager::with_event_sender(event_tx.clone());
 
         // Only start gRPC client for main window
+        let mut settings_sync = None;
+        let mut schedule_panel = None;
+        let mut approvals_panel = None;
+        let mut bot_url = None;
         if matches!(window_mode, WindowMode::Main) {
             // Ensure server is running first
             let server_url = tokio::runtime::Handle::current()
@@ -69,13 +87,39 @@ impl DesktopApp {
                 event_tx.clone(),
                 command_rx,
             );
+
+            // Share UI, audio and overlay settings with the VR overlay
+            settings_sync = Some(SettingsSync::start("maowbot-gui", server_url.clone(), event_tx.clone()));
+            approvals_panel = Some(approvals_panel::ApprovalsPanel::new(server_url.clone()));
+            schedule_panel = Some(schedule_panel::SchedulePanel::new(server_url.clone()));
+            bot_url = Some(server_url);
         }
 
         let process_manager = Arc::new(Mutex::new(process_manager));
 
+        let mut renderer = egui_renderer::EguiRenderer::new(window_mode.clone());
+        if let Some(panel) = schedule_panel {
+            renderer.set_schedule_panel(panel);
+        }
+        if let Some(panel) = approvals_panel {
+            renderer.set_approvals_panel(panel);
+        }
+        if let Some(url) = bot_url {
+            renderer.chat_composer().connect(url);
+        }
+        let synced_settings = {
+            let settings = renderer.get_settings();
+            let settings = settings.lock().unwrap();
+            SyncedSettings {
+                ui: settings.ui_settings.clone(),
+                audio: settings.audio_settings.clone(),
+                overlay: settings.stream_overlay_settings.clone(),
+            }
+        };
+
         Ok(Self {
             state,
-            renderer: egui_renderer::EguiRenderer::new(window_mode.clone()),
+            renderer,
             process_manager,
             event_rx,
             event_tx,
@@ -84,22 +128,51 @@ impl DesktopApp {
             secondary_window_id: None,
             should_open_secondary: false,
             secondary_renderer: None,
+            settings_sync,
+            synced_settings,
         })
     }
 
-    fn handle_events(&mut self) {
+    fn handle_events(&mut self, ctx: &egui::Context) {
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
                 AppEvent::Chat(chat_event) => {
+                    self.renderer.chat_composer().record(&chat_event);
                     let mut chat_state = self.state.chat_state.lock().unwrap();
                     chat_state.add_message(chat_event);
                 }
                 AppEvent::OverlayStatusChanged(running) => {
                     *self.state.overlay_running.lock().unwrap() = running;
                 }
-                AppEvent::GrpcStatusChanged(connected) => {
-                    *self.state.grpc_connected.lock().unwrap() = connected;
+                AppEvent::ProcessCrashed { process, exit, report, restarting } => {
+                    let mut notice = format!("{} crashed ({})", process.name(), exit);
+                    if restarting {
+                        notice.push_str(" — restarting");
+                    }
+                    if let Some(report) = report {
+                        notice.push_str(&format!("\nReport: {}", report.display()));
+                    }
+                    *self.state.process_notice.lock().unwrap() = Some(notice);
+                }
+                AppEvent::GrpcStatusChanged(status) => {
+                    *self.state.grpc_status.lock().unwrap() = status;
+                }
+                AppEvent::HeartRate(_) => {}
+                AppEvent::CrowdVote(_) => {}
+                AppEvent::OpsAlert { severity, title, message } => {
+                    let mut notice = format!("[{}] {}", severity.to_uppercase(), title);
+                    if !message.is_empty() {
+                        notice.push_str(&format!("\n{}", message));
+                    }
+                    *self.state.process_notice.lock().unwrap() = Some(notice);
+                    let attention = if severity == "critical" {
+                        egui::UserAttentionType::Critical
+                    } else {
+                        egui::UserAttentionType::Informational
+                    };
+                    ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(attention));
                 }
+                AppEvent::SettingsChanged(shared) => self.apply_shared_settings(shared),
                 AppEvent::Shutdown => {
                     // Don't exit immediately, let the app handle it
                 }
@@ -107,6 +180,46 @@ impl DesktopApp {
         }
     }
 
+    /// Takes in settings another client saved, without sending them back.
+    fn apply_shared_settings(&mut self, shared: SharedSettings) {
+        let settings = self.renderer.get_settings();
+        let mut settings = settings.lock().unwrap();
+        match shared {
+            SharedSettings::Ui(ui) => {
+                settings.ui_settings = ui.clone();
+                self.synced_settings.ui = ui;
+            }
+            SharedSettings::Audio(audio) => {
+                settings.audio_settings = audio.clone();
+                self.synced_settings.audio = audio;
+            }
+            SharedSettings::Overlay(overlay) => {
+                settings.stream_overlay_settings = overlay.clone();
+                self.synced_settings.overlay = overlay;
+            }
+        }
+    }
+
+    /// Saves whichever shared settings the user changed this frame.
+    fn publish_settings_changes(&mut self) {
+        let Some(sync) = &self.settings_sync else { return };
+        let settings = self.renderer.get_settings();
+        let settings = settings.lock().unwrap();
+        let synced = &mut self.synced_settings;
+        if settings.ui_settings != synced.ui {
+            synced.ui = settings.ui_settings.clone();
+            sync.publish(SharedSettings::Ui(synced.ui.clone()));
+        }
+        if settings.audio_settings != synced.audio {
+            synced.audio = settings.audio_settings.clone();
+            sync.publish(SharedSettings::Audio(synced.audio.clone()));
+        }
+        if settings.stream_overlay_settings != synced.overlay {
+            synced.overlay = settings.stream_overlay_settings.clone();
+            sync.publish(SharedSettings::Overlay(synced.overlay.clone()));
+        }
+    }
+
     fn cleanup(&self) {
         tracing::info!("Cleaning up before exit...");
 
@@ -118,7 +231,7 @@ impl DesktopApp {
             // Use tokio::task::block_in_place to run async code in sync context
             tokio::task::block_in_place(|| {
                 tokio::runtime::Handle::current().block_on(async move {
-                    let manager = pm.lock().unwrap();
+                    let manager = pm.lock().unwrap().clone();
                     match tokio::time::timeout(
                         tokio::time::Duration::from_secs(5),
                         manager.stop(ProcessType::Overlay)
@@ -149,7 +262,7 @@ impl DesktopApp {
 impl eframe::App for DesktopApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Handle events
-        self.handle_events();
+        self.handle_events(ctx);
 
         // Handle deferred secondary window opening
         if self.should_open_secondary {
@@ -163,10 +276,12 @@ impl eframe::App for DesktopApp {
             let viewport_id = egui::ViewportId::from_hash_of("secondary_window");
             self.secondary_window_id = Some(viewport_id);
             
-            if let Some(renderer) = &mut self.secondary_renderer {
+            if let Some(_renderer) = &mut self.secondary_renderer {
                 let state_clone = self.state.clone();
                 let main_ctx = ctx.clone();
                 let settings = self.renderer.get_settings();
+                let schedule_panel = self.renderer.schedule_panel();
+                let approvals_panel = self.renderer.approvals_panel();
                 ctx.show_viewport_deferred(
                     viewport_id,
                     egui::ViewportBuilder::default()
@@ -176,6 +291,8 @@ impl eframe::App for DesktopApp {
                     move |ctx, _class| {
                         // Create a renderer with shared settings
                         let mut temp_renderer = egui_renderer::EguiRenderer::new_with_settings(WindowMode::Secondary, settings.clone());
+                        temp_renderer.share_schedule_panel(schedule_panel.clone());
+                        temp_renderer.share_approvals_panel(approvals_panel.clone());
                         temp_renderer.render_secondary_window(ctx, &state_clone);
                         
                         // Check if we just docked and need to notify main window
@@ -222,6 +339,8 @@ impl eframe::App for DesktopApp {
             }
         }
 
+        self.publish_settings_changes();
+
         // Request repaint for animations and state changes
         ctx.request_repaint();
         
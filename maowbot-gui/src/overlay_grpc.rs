@@ -45,9 +45,12 @@ fn setup_ipc(mut cmds: Commands) {
         if url.starts_with("https://") {
             let ca = tokio::fs::read(&ca_path).await.expect("Failed to read CA");
             let ca_cert = Certificate::from_pem(ca);
-            let tls = ClientTlsConfig::new()
+            let mut tls = ClientTlsConfig::new()
                 .ca_certificate(ca_cert)
                 .domain_name("localhost");
+            if let Some(identity) = maowbot_common_ui::grpc_client::client_identity() {
+                tls = tls.identity(identity);
+            }
             endpoint = endpoint.tls_config(tls).expect("Invalid TLS config");
         }
 
@@ -92,7 +95,7 @@ fn forward_inbound(mut rx: ResMut<Rx>, mut writer: EventWriter<ChatEvent>) {
         if let Some(RespPayload::ChatMessage(cm)) = msg.payload {
             writer.write(ChatEvent {
                 channel: cm.channel,
-                author:  cm.user,
+                author:  if cm.display_name.is_empty() { cm.user } else { cm.display_name },
                 body:    cm.text,
             });
         }
@@ -0,0 +1,305 @@
+//! The "Schedule" tab: the broadcaster's Twitch stream schedule, with forms to
+//! add, edit, cancel and remove segments. Requests run on the tokio runtime
+//! with their own connection, so a slow Twitch answer doesn't stall the UI.
+
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, NaiveDateTime};
+use egui::{Color32, RichText, ScrollArea};
+use maowbot_common_ui::GrpcClient;
+use maowbot_common_ui::commands::twitch::TwitchCommands;
+use maowbot_proto::maowbot::services::ScheduleSegment;
+use maowbot_proto::prost_types::Timestamp;
+
+/// What the last request left behind.
+#[derive(Default)]
+struct ScheduleView {
+    segments: Vec<ScheduleSegment>,
+    loaded: bool,
+    busy: bool,
+    /// The last error, or what the last change did
+    status: Option<(bool, String)>,
+}
+
+/// The add/edit form; times are UTC.
+struct SegmentForm {
+    /// The segment being edited, None when adding
+    editing: Option<String>,
+    date: String,
+    time: String,
+    minutes: String,
+    title: String,
+    category: String,
+    weekly: bool,
+}
+
+impl Default for SegmentForm {
+    fn default() -> Self {
+        Self {
+            editing: None,
+            date: String::new(),
+            time: "18:00".to_string(),
+            minutes: "120".to_string(),
+            title: String::new(),
+            category: String::new(),
+            weekly: false,
+        }
+    }
+}
+
+impl SegmentForm {
+    fn edit(segment: &ScheduleSegment) -> Self {
+        let start = segment.start_time.as_ref().and_then(|t| DateTime::from_timestamp(t.seconds, 0));
+        Self {
+            editing: Some(segment.segment_id.clone()),
+            date: start.map(|at| at.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            time: start.map(|at| at.format("%H:%M").to_string()).unwrap_or_default(),
+            minutes: segment.duration_minutes.to_string(),
+            title: segment.title.clone(),
+            category: segment.category.clone(),
+            weekly: segment.is_recurring,
+        }
+    }
+
+    fn start_time(&self) -> Result<Timestamp, String> {
+        let at = NaiveDateTime::parse_from_str(&format!("{} {}", self.date.trim(), self.time.trim()), "%Y-%m-%d %H:%M")
+            .map_err(|_| "Date and time must look like 2026-11-07 and 18:00".to_string())?;
+        Ok(Timestamp { seconds: at.and_utc().timestamp(), nanos: 0 })
+    }
+
+    fn duration(&self) -> Result<i32, String> {
+        self.minutes.trim().parse().map_err(|_| "Duration must be a number of minutes".to_string())
+    }
+}
+
+enum Request {
+    Load { refresh: bool },
+    Create(SegmentForm),
+    Update(String, SegmentForm),
+    SetCanceled(String, bool),
+    Delete(String),
+}
+
+pub struct SchedulePanel {
+    url: String,
+    runtime: tokio::runtime::Handle,
+    view: Arc<Mutex<ScheduleView>>,
+    form: SegmentForm,
+}
+
+impl SchedulePanel {
+    /// Must be created inside the tokio runtime.
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            runtime: tokio::runtime::Handle::current(),
+            view: Arc::new(Mutex::new(ScheduleView::default())),
+            form: SegmentForm::default(),
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui) {
+        let needs_load = {
+            let view = self.view.lock().unwrap();
+            !view.loaded && !view.busy
+        };
+        if needs_load {
+            self.send(ui.ctx(), Request::Load { refresh: false });
+        }
+
+        ui.horizontal(|ui| {
+            ui.heading("Stream Schedule");
+            let busy = self.view.lock().unwrap().busy;
+            if busy {
+                ui.spinner();
+            } else if ui.button("⟳ Refresh").clicked() {
+                self.send(ui.ctx(), Request::Load { refresh: true });
+            }
+        });
+        if let Some((ok, message)) = self.view.lock().unwrap().status.clone() {
+            let color = if ok { Color32::from_rgb(0, 200, 0) } else { Color32::from_rgb(255, 100, 100) };
+            ui.colored_label(color, message);
+        }
+        ui.separator();
+
+        self.render_form(ui);
+        ui.separator();
+
+        let segments = self.view.lock().unwrap().segments.clone();
+        if segments.is_empty() {
+            ui.label("Nothing on the schedule.");
+            return;
+        }
+        ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            egui::Grid::new("schedule_segments").striped(true).num_columns(5).show(ui, |ui| {
+                ui.label(RichText::new("Start (UTC)").strong());
+                ui.label(RichText::new("Length").strong());
+                ui.label(RichText::new("Title").strong());
+                ui.label(RichText::new("Category").strong());
+                ui.label("");
+                ui.end_row();
+
+                for segment in &segments {
+                    let start = segment.start_time.as_ref()
+                        .and_then(|t| DateTime::from_timestamp(t.seconds, 0))
+                        .map(|at| at.format("%a %Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default();
+                    let mut when = RichText::new(start);
+                    if segment.is_canceled {
+                        when = when.strikethrough();
+                    }
+                    ui.label(when);
+                    ui.label(format!("{}m{}", segment.duration_minutes, if segment.is_recurring { " weekly" } else { "" }));
+                    ui.label(&segment.title);
+                    ui.label(&segment.category);
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Edit").clicked() {
+                            self.form = SegmentForm::edit(segment);
+                        }
+                        let (label, cancel) = if segment.is_canceled { ("Restore", false) } else { ("Cancel", true) };
+                        if ui.small_button(label).on_hover_text("Only this occurrence").clicked() {
+                            self.send(ui.ctx(), Request::SetCanceled(segment.segment_id.clone(), cancel));
+                        }
+                        if ui.small_button("Delete").on_hover_text("Every occurrence").clicked() {
+                            self.send(ui.ctx(), Request::Delete(segment.segment_id.clone()));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn render_form(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new(if self.form.editing.is_some() { "Edit segment" } else { "Add segment" }).strong());
+        egui::Grid::new("schedule_form").num_columns(2).show(ui, |ui| {
+            ui.label("Date (UTC)");
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.form.date).hint_text("2026-11-07").desired_width(90.0));
+                ui.add(egui::TextEdit::singleline(&mut self.form.time).hint_text("18:00").desired_width(50.0));
+            });
+            ui.end_row();
+            ui.label("Minutes");
+            ui.add(egui::TextEdit::singleline(&mut self.form.minutes).desired_width(50.0));
+            ui.end_row();
+            ui.label("Title");
+            ui.text_edit_singleline(&mut self.form.title);
+            ui.end_row();
+            ui.label("Category");
+            ui.text_edit_singleline(&mut self.form.category);
+            ui.end_row();
+            if self.form.editing.is_none() {
+                ui.label("");
+                ui.checkbox(&mut self.form.weekly, "Repeat weekly");
+                ui.end_row();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            match self.form.editing.clone() {
+                Some(id) => {
+                    if ui.button("Save").clicked() {
+                        let form = std::mem::take(&mut self.form);
+                        self.send(ui.ctx(), Request::Update(id, form));
+                    }
+                    if ui.button("Stop editing").clicked() {
+                        self.form = SegmentForm::default();
+                    }
+                }
+                None => {
+                    if ui.button("Add").clicked() {
+                        let form = std::mem::take(&mut self.form);
+                        self.send(ui.ctx(), Request::Create(form));
+                    }
+                }
+            }
+        });
+    }
+
+    fn send(&self, ctx: &egui::Context, request: Request) {
+        let url = self.url.clone();
+        let view = self.view.clone();
+        let ctx = ctx.clone();
+        view.lock().unwrap().busy = true;
+
+        self.runtime.spawn(async move {
+            let (done, refresh) = match request {
+                Request::Load { refresh } => (None, refresh),
+                change => match change_schedule(&url, change).await {
+                    Ok(done) => (Some(done), false),
+                    Err(e) => {
+                        let mut view = view.lock().unwrap();
+                        view.busy = false;
+                        view.status = Some((false, e));
+                        ctx.request_repaint();
+                        return;
+                    }
+                },
+            };
+            // Changes are followed by a fresh copy of the schedule
+            let loaded = load_schedule(&url, refresh).await;
+            {
+                let mut view = view.lock().unwrap();
+                view.busy = false;
+                view.loaded = true;
+                match (loaded, done) {
+                    (Ok(segments), done) => {
+                        view.segments = segments;
+                        view.status = done.map(|done| (true, done));
+                    }
+                    (Err(e), Some(done)) => view.status = Some((false, format!("{}, but reloading failed: {}", done, e))),
+                    (Err(e), None) => view.status = Some((false, format!("Loading the schedule failed: {}", e))),
+                }
+            }
+            ctx.request_repaint();
+        });
+    }
+}
+
+/// Makes a change and says what it did.
+async fn change_schedule(url: &str, request: Request) -> Result<String, String> {
+    let client = GrpcClient::connect(url).await.map_err(|e| format!("Can't reach the bot: {}", e))?;
+    match request {
+        Request::Load { .. } => Ok(String::new()),
+        Request::Create(form) => {
+            let start = form.start_time()?;
+            TwitchCommands::create_schedule_segment(&client, start, form.duration()?, form.title.trim(), form.category.trim(), form.weekly)
+                .await
+                .map_err(|e| format!("Adding the segment failed: {}", e))?;
+            Ok("Segment added".to_string())
+        }
+        Request::Update(id, form) => {
+            let segment = ScheduleSegment {
+                start_time: Some(form.start_time()?),
+                duration_minutes: form.duration()?,
+                title: form.title.trim().to_string(),
+                category: form.category.trim().to_string(),
+                ..Default::default()
+            };
+            let mut fields = vec!["start_time", "duration_minutes", "title"];
+            if !segment.category.is_empty() {
+                fields.push("category");
+            }
+            TwitchCommands::update_schedule_segment(&client, &id, segment, &fields).await
+                .map_err(|e| format!("Saving the segment failed: {}", e))?;
+            Ok("Segment saved".to_string())
+        }
+        Request::SetCanceled(id, canceled) => {
+            let segment = ScheduleSegment { is_canceled: canceled, ..Default::default() };
+            TwitchCommands::update_schedule_segment(&client, &id, segment, &["is_canceled"]).await
+                .map_err(|e| format!("Changing the segment failed: {}", e))?;
+            Ok(if canceled { "Occurrence canceled" } else { "Occurrence restored" }.to_string())
+        }
+        Request::Delete(id) => {
+            TwitchCommands::delete_schedule_segment(&client, &id).await
+                .map_err(|e| format!("Deleting the segment failed: {}", e))?;
+            Ok("Segment deleted".to_string())
+        }
+    }
+}
+
+async fn load_schedule(url: &str, refresh: bool) -> Result<Vec<ScheduleSegment>, String> {
+    let client = GrpcClient::connect(url).await.map_err(|e| format!("Can't reach the bot: {}", e))?;
+    TwitchCommands::list_schedule_segments(&client, refresh).await
+        .map(|result| result.data.segments)
+        .map_err(|e| e.to_string())
+}
@@ -3,7 +3,6 @@ pub(crate) use maowbot_common_ui::{
     SettingsTab, ChatSide, StreamerListEntry,
     UISettings, AudioSettings, StreamOverlaySettings
 };
-use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct Settings {
@@ -413,12 +412,12 @@ impl Settings {
                     ui.label("Width:");
                     ui.add(egui::DragValue::new(&mut self.stream_overlay_settings.chat_width)
                         .speed(1.0)
-                        .clamp_range(100.0..=1000.0)
+                        .range(100.0..=1000.0)
                         .suffix("px"));
                     ui.label("Height:");
                     ui.add(egui::DragValue::new(&mut self.stream_overlay_settings.chat_height)
                         .speed(1.0)
-                        .clamp_range(100.0..=1200.0)
+                        .range(100.0..=1200.0)
                         .suffix("px"));
                 });
             });
@@ -436,7 +435,7 @@ impl Settings {
             ui.indent("alert_settings", |ui| {
                 ui.horizontal(|ui| {
                     ui.label("Position:");
-                    egui::ComboBox::from_id_source("alert_position")
+                    egui::ComboBox::from_id_salt("alert_position")
                         .selected_text(&self.stream_overlay_settings.alert_position)
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut self.stream_overlay_settings.alert_position, "Top Center".to_string(), "Top Center");
@@ -482,10 +481,6 @@ impl Settings {
         self.show_settings = !self.show_settings;
     }
 
-    pub fn is_open(&self) -> bool {
-        self.show_settings
-    }
-    
     pub fn get_ui_settings(&self) -> UISettings {
         self.ui_settings.clone()
     }
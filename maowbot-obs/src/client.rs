@@ -234,4 +234,17 @@ impl ObsClient {
             None => Err(ObsError::InstanceNotConnected(self.instance.instance_number)),
         }
     }
-}
\ No newline at end of file
+    
+    /// Saves the last seconds held by OBS's replay buffer, which has to be running.
+    pub async fn save_replay_buffer(&self) -> Result<()> {
+        let client_guard = self.client.read().await;
+        match client_guard.as_ref() {
+            Some(client) => {
+                client.replay_buffer().save().await
+                    .map_err(|e| ObsError::WebSocketError(e.to_string()))?;
+                Ok(())
+            }
+            None => Err(ObsError::InstanceNotConnected(self.instance.instance_number)),
+        }
+    }
+}
@@ -1,13 +1,14 @@
 // maowbot-osc/src/lib.rs
 use std::net::{UdpSocket, SocketAddr};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::{Mutex, mpsc};
 use thiserror::Error;
 use tokio::task::JoinHandle;
 use crate::oscquery::{OscQueryClient, OscQueryServer};
 use crate::vrchat::{discover_vrchat, query_vrchat_oscquery};
 use rosc::{OscPacket, OscType};
-use tracing::{debug, trace, info, error, warn};
+use tracing::{debug, trace, info, warn};
 pub mod oscquery;
 pub mod vrchat;
 pub mod robo; // left as-is
@@ -37,6 +38,27 @@ pub struct VRChatConnectionInfo {
     /// typically 9001 or random.
     pub osc_receive_port: u16,
 }
+/// Monotonic packet counters; callers derive rates by diffing snapshots.
+#[derive(Debug, Default)]
+pub struct OscCounters {
+    pub packets_received: AtomicU64,
+    pub packets_sent: AtomicU64,
+}
+impl OscCounters {
+    pub fn record_received(&self) {
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn record_sent(&self) {
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Returns (received, sent).
+    pub fn snapshot(&self) -> (u64, u64) {
+        (
+            self.packets_received.load(Ordering::Relaxed),
+            self.packets_sent.load(Ordering::Relaxed),
+        )
+    }
+}
 /// A top-level manager that orchestrates the OSC server, VRChat toggles, etc.
 pub struct MaowOscManager {
     pub inner: Arc<Mutex<OscManagerInner>>,
@@ -47,6 +69,7 @@ pub struct MaowOscManager {
     pub vrchat_info: Arc<Mutex<Option<VRChatConnectionInfo>>>,
     pub vrchat_dest: Arc<Mutex<Option<String>>>,
     pub robot_dest: Arc<Mutex<Option<String>>>,
+    pub counters: Arc<OscCounters>,
 }
 pub struct OscManagerInner {
     /// The UDP port on which we are currently listening for OSC
@@ -79,7 +102,7 @@ pub struct OscReceiver {
 impl OscReceiver {
     /// Bind a UDP socket on the given port. If `port == 0`, we bind an ephemeral port.
     /// The actual bound port is extracted from `socket.local_addr()`.
-    pub fn new(port: u16) -> Result<Self> {
+    pub fn new(port: u16, counters: Arc<OscCounters>) -> Result<Self> {
         let (tx, rx) = mpsc::unbounded_channel();
         let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
 
@@ -121,6 +144,7 @@ impl OscReceiver {
                             Ok((size, addr)) => {
                                 match rosc::decoder::decode_udp(&buf[..size]) {
                                     Ok((_remaining, packet)) => {
+                                        counters.record_received();
                                         match &packet {
                                             OscPacket::Message(msg) => {
                                                 if !is_common_osc_message(&msg.addr) {
@@ -173,6 +197,12 @@ impl OscReceiver {
         }
     }
 }
+impl Default for MaowOscManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaowOscManager {
     pub fn new() -> Self {
         let inner = OscManagerInner {
@@ -193,6 +223,7 @@ impl MaowOscManager {
             vrchat_info: Arc::new(Mutex::new(None)),
             vrchat_dest: Arc::new(Mutex::new(None)),
             robot_dest: Arc::new(Mutex::new(None)),
+            counters: Arc::new(OscCounters::default()),
         }
     }
     /// Return a status snapshot.
@@ -267,7 +298,7 @@ impl MaowOscManager {
         }
 
         // 1) Start ephemeral OSC receiver for inbound data from VRChat
-        let receiver = OscReceiver::new(0)?; // 0 => ephemeral
+        let receiver = OscReceiver::new(0, self.counters.clone())?; // 0 => ephemeral
         let actual_port = receiver.port();
         {
             let mut lock_inner = self.inner.lock().await;
@@ -402,6 +433,7 @@ impl MaowOscManager {
         }
         sock.send_to(&buf, dest_str)
             .map_err(|e| OscError::IoError(format!("Send error: {e}")))?;
+        self.counters.record_sent();
         Ok(())
     }
     /// Single-arg helpers
@@ -445,6 +477,14 @@ impl MaowOscManager {
             Err(OscError::Generic("No VRChat watcher configured".into()))
         }
     }
+    /// The config of the avatar the VRChat watcher considers current.
+    pub async fn current_avatar_config(&self) -> Result<Option<crate::vrchat::VrchatAvatarConfig>> {
+        let w = self.vrchat_watcher.as_ref()
+            .ok_or_else(|| OscError::Generic("No VRChat watcher configured".into()))?;
+        let mut w = w.lock().await;
+        w.ensure_loaded()?;
+        Ok(w.current_avatar().map(|k| k.config.clone()))
+    }
     pub async fn take_osc_receiver(&self) -> Option<mpsc::UnboundedReceiver<OscPacket>> {
         let mut r = self.osc_receiver.lock().await;
         r.as_mut()?.take_receiver()
@@ -480,6 +520,7 @@ impl MaowOscManager {
         }
         sock.send_to(&buf, dest_str)
             .map_err(|e| OscError::IoError(format!("Send error: {e}")))?;
+        self.counters.record_sent();
         Ok(())
     }
 }
@@ -4,7 +4,7 @@
 use crate::{OscError, Result};
 use tokio::sync::Mutex;
 use std::sync::Arc;
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 
 /// Holds discovered VRChat addresses, if any
 #[derive(Default)]
@@ -23,6 +23,12 @@ pub struct OscQueryClient {
     pub is_initialized: bool,
 }
 
+impl Default for OscQueryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OscQueryClient {
     pub fn new() -> Self {
         Self {
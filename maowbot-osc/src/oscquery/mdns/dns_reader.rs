@@ -1,7 +1,7 @@
 use std::io::Cursor;
 use std::collections::HashMap;
 use byteorder::{BigEndian, ReadBytesExt};
-use std::io::{Read, Result as IoResult};
+use std::io::Read;
 use tracing::trace;
 
 /// A simplified "big-endian" DNS reader. This parallels the C# `BigReader` code.
@@ -45,10 +45,7 @@ impl DnsReader {
         let mut first_pass = true;
 
         loop {
-            let b = match self.inner.read_u8() {
-                Ok(v) => v,
-                Err(e) => return Err(e),
-            };
+            let b = self.inner.read_u8()?;
 
             if b == 0 {
                 // End of name.
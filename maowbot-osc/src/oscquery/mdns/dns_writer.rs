@@ -6,6 +6,12 @@ pub struct DnsWriter {
     pub data: Cursor<Vec<u8>>,
 }
 
+impl Default for DnsWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DnsWriter {
     pub fn new() -> Self {
         DnsWriter {
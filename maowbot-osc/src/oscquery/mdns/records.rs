@@ -1,7 +1,6 @@
 use super::dns_reader::DnsReader;
 use super::dns_writer::DnsWriter;
 use std::io::Result as IoResult;
-use std::io::Error;
 use tracing::trace; // Added for verbose logging
 
 /// DNS TYPE constants
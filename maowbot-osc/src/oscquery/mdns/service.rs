@@ -127,7 +127,7 @@ impl MdnsService {
     pub fn start(&mut self) {
         let socket = self.socket.try_clone().expect("Failed to clone UDP socket");
         let adv_map = self.advertised.clone();
-        let mut stop_rx = self.stop_tx.subscribe();
+        let stop_rx = self.stop_tx.subscribe();
 
         let handle = tokio::spawn(async move {
             let mut buf = [0u8; 4096];
@@ -214,7 +214,7 @@ impl MdnsService {
         let socket = self.socket.try_clone().expect("Failed to clone UDP socket");
         let adv_map = self.advertised.clone();
         let disc_map = self.discovered.clone();
-        let mut stop_rx = self.stop_tx.subscribe();
+        let stop_rx = self.stop_tx.subscribe();
 
         let handle = tokio::spawn(async move {
             let mut buf = [0u8; 4096];
@@ -348,13 +348,11 @@ fn parse_mdns_response(packet: &DnsPacket, discovered: &Arc<Mutex<Vec<Advertised
                     }
                     srv_map.insert(full, (*port, t_fqdn));
                 }
-                RData::ARecord(ip_bytes) => {
-                    if ip_bytes.len() == 4 {
-                        let ip = Ipv4Addr::new(ip_bytes[0], ip_bytes[1], ip_bytes[2], ip_bytes[3]);
-                        let full = ans.labels.join(".");
-                        trace!("Found A => name:{} ip:{}", full, ip);
-                        a_map.insert(full, ip);
-                    }
+                RData::ARecord(ip_bytes) if ip_bytes.len() == 4 => {
+                    let ip = Ipv4Addr::new(ip_bytes[0], ip_bytes[1], ip_bytes[2], ip_bytes[3]);
+                    let full = ans.labels.join(".");
+                    trace!("Found A => name:{} ip:{}", full, ip);
+                    a_map.insert(full, ip);
                 }
                 _ => {}
             }
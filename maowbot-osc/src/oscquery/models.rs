@@ -49,6 +49,8 @@ pub struct OSCMethod {
 
 /// Host info response for /HOST_INFO
 // We replace the old OSCQueryHostInfo with a custom struct that fixes field ordering.
+// Field names are the OSCQuery JSON keys, here and below.
+#[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OSCQueryHostInfo {
     pub NAME: String,
@@ -59,6 +61,7 @@ pub struct OSCQueryHostInfo {
 }
 
 // A small helper struct so we can preserve extension ordering exactly.
+#[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HostExtensions {
     pub ACCESS: bool,
@@ -69,6 +72,7 @@ pub struct HostExtensions {
 }
 
 /// Node in the OSCQuery “directory” tree
+#[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OSCQueryNode {
     /// e.g. "Root Container"
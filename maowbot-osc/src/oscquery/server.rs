@@ -5,13 +5,11 @@ use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::{Mutex, watch};
 use warp::http::StatusCode;
-use warp::{Filter, Rejection, Reply};
-use tracing::{info, error};
-use futures_util::stream::TryStreamExt;
-use tokio_util::compat::TokioAsyncWriteCompatExt;
+use warp::{Filter, Rejection};
+use tracing::info;
 
 use crate::{Result, OscError};
-use crate::oscquery::mdns::{MdnsService, AdvertisedService};
+use crate::oscquery::mdns::MdnsService;
 use crate::oscquery::models::{
     HostExtensions,
     OSCMethod,
@@ -420,6 +418,6 @@ impl OscQueryServer {
 /// Helper for generating random 6-digit hex strings like "A1B2C3"
 fn random_hex_6() -> String {
     use rand::Rng;
-    let r: u32 = rand::thread_rng().gen_range(0..=0xFFFFFF);
+    let r: u32 = rand::rng().random_range(0..=0xFFFFFF);
     format!("{:06X}", r)
 }
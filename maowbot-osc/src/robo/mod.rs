@@ -12,6 +12,12 @@ pub struct RoboControlSystem {
     pub is_active: bool,
 }
 
+impl Default for RoboControlSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RoboControlSystem {
     pub fn new() -> Self {
         Self {
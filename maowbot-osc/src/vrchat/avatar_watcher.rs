@@ -224,7 +224,7 @@ impl AvatarWatcher {
         match packet {
             OscPacket::Message(msg) => {
                 if msg.addr == "/avatar/change" {
-                    if let Some(OscType::String(avatar_id)) = msg.args.get(0) {
+                    if let Some(OscType::String(avatar_id)) = msg.args.first() {
                         tracing::info!("Avatar change detected: {}", avatar_id);
 
                         // Store the current avatar ID
@@ -264,7 +264,7 @@ impl AvatarWatcher {
                     match inner_packet {
                         OscPacket::Message(msg) => {
                             if msg.addr == "/avatar/change" {
-                                if let Some(OscType::String(avatar_id)) = msg.args.get(0) {
+                                if let Some(OscType::String(avatar_id)) = msg.args.first() {
                                     tracing::info!("Avatar change detected (in bundle): {}", avatar_id);
 
                                     // Store the current avatar ID
@@ -294,6 +294,24 @@ impl AvatarWatcher {
         self.current_avatar_id.as_ref()
     }
 
+    /// The avatar being worn. Until an `/avatar/change` arrives, this is the one
+    /// whose config VRChat wrote most recently, which is the last avatar loaded.
+    pub fn current_avatar(&self) -> Option<&KnownAvatar> {
+        if let Some(known) = self.current_avatar_id.as_ref().and_then(|id| self.known_avatars.get(id)) {
+            return Some(known);
+        }
+        self.known_avatars.values()
+            .max_by_key(|k| std::fs::metadata(&k.path).and_then(|m| m.modified()).ok())
+    }
+
+    /// Rescans the folder if nothing has been loaded yet.
+    pub fn ensure_loaded(&mut self) -> Result<()> {
+        if self.known_avatars.is_empty() {
+            self.reload_all_avatars()?;
+        }
+        Ok(())
+    }
+
     /// Reload all `.json` files from the folder into `known_avatars`.
     pub(crate) fn reload_all_avatars(&mut self) -> Result<()> {
         self.known_avatars.clear();
@@ -305,34 +323,32 @@ impl AvatarWatcher {
         let entries = std::fs::read_dir(&self.folder)
             .map_err(|e| OscError::AvatarConfigError(format!("Unable to read dir: {:?}", e)))?;
 
-        for entry in entries {
-            if let Ok(de) = entry {
-                let p = de.path();
-                if p.extension().map(|ext| ext == "json").unwrap_or(false) {
-                    // Use tokio block_in_place to allow for retries
-                    tokio::task::block_in_place(|| {
-                        // Try a few times with delay
-                        for attempt in 1..=3 {
-                            match parse_vrchat_avatar_config(&p) {
-                                Ok(cfg) => {
-                                    let av_id = cfg.id.clone();
-                                    let known = KnownAvatar { path: p.clone(), config: cfg };
-                                    self.known_avatars.insert(av_id, known);
-                                    break;
-                                }
-                                Err(e) => {
-                                    if attempt < 3 {
-                                        tracing::debug!("Attempt {} failed to parse {}: {}. Retrying...",
-                                            attempt, p.display(), e);
-                                        thread::sleep(Duration::from_millis(200));
-                                    } else {
-                                        tracing::warn!("Failed to parse {}: {}", p.display(), e);
-                                    }
+        for de in entries.flatten() {
+            let p = de.path();
+            if p.extension().map(|ext| ext == "json").unwrap_or(false) {
+                // Use tokio block_in_place to allow for retries
+                tokio::task::block_in_place(|| {
+                    // Try a few times with delay
+                    for attempt in 1..=3 {
+                        match parse_vrchat_avatar_config(&p) {
+                            Ok(cfg) => {
+                                let av_id = cfg.id.clone();
+                                let known = KnownAvatar { path: p.clone(), config: cfg };
+                                self.known_avatars.insert(av_id, known);
+                                break;
+                            }
+                            Err(e) => {
+                                if attempt < 3 {
+                                    tracing::debug!("Attempt {} failed to parse {}: {}. Retrying...",
+                                        attempt, p.display(), e);
+                                    thread::sleep(Duration::from_millis(200));
+                                } else {
+                                    tracing::warn!("Failed to parse {}: {}", p.display(), e);
                                 }
                             }
                         }
-                    });
-                }
+                    }
+                });
             }
         }
 
@@ -356,21 +372,21 @@ impl FileChangeEvent {
     pub fn new(event: Event) -> Self {
         match event.kind {
             EventKind::Create(CreateKind::File) => {
-                if let Some(path) = event.paths.get(0) {
+                if let Some(path) = event.paths.first() {
                     FileChangeEvent::Added(path.clone())
                 } else {
                     FileChangeEvent::Other(event)
                 }
             }
             EventKind::Modify(ModifyKind::Data(_)) => {
-                if let Some(path) = event.paths.get(0) {
+                if let Some(path) = event.paths.first() {
                     FileChangeEvent::Modified(path.clone())
                 } else {
                     FileChangeEvent::Other(event)
                 }
             }
             EventKind::Remove(RemoveKind::File) => {
-                if let Some(path) = event.paths.get(0) {
+                if let Some(path) = event.paths.first() {
                     FileChangeEvent::Removed(path.clone())
                 } else {
                     FileChangeEvent::Other(event)
@@ -422,20 +438,18 @@ pub fn handle_osc_packet(packet: OscPacket, known_map_ptr: Arc<Mutex<HashMap<Str
             tracing::trace!("Received OSC message: {} with {} args", msg.addr, msg.args.len());
 
             if msg.addr == "/avatar/change" {
-                if let Some(arg) = msg.args.get(0) {
-                    if let OscType::String(avatar_id_str) = arg {
-                        let avatar_id = avatar_id_str.clone();
-                        tracing::info!("DETECTED AVATAR CHANGE => {}", avatar_id);
-                        tokio::spawn(async move {
-                            let map_lock = known_map_ptr.lock().await;
-                            if let Some(kav) = map_lock.get(&avatar_id) {
-                                let menu = AvatarToggleMenu::new(&kav.config);
-                                menu.print_menu();
-                            } else {
-                                tracing::warn!("No local config for avatar_id={}", avatar_id);
-                            }
-                        });
-                    }
+                if let Some(OscType::String(avatar_id_str)) = msg.args.first() {
+                    let avatar_id = avatar_id_str.clone();
+                    tracing::info!("DETECTED AVATAR CHANGE => {}", avatar_id);
+                    tokio::spawn(async move {
+                        let map_lock = known_map_ptr.lock().await;
+                        if let Some(kav) = map_lock.get(&avatar_id) {
+                            let menu = AvatarToggleMenu::new(&kav.config);
+                            menu.print_menu();
+                        } else {
+                            tracing::warn!("No local config for avatar_id={}", avatar_id);
+                        }
+                    });
                 }
             }
         }
@@ -28,15 +28,15 @@ impl ChatboxMessage {
     }
 }
 
-/// Additional chatbox-related methods to be invoked. You can implement them
-/// as free functions or as trait impl. Here, for convenience, we show them
-/// as top-level standalone functions.
+// Additional chatbox-related methods to be invoked. You can implement them
+// as free functions or as trait impl. Here, for convenience, we show them
+// as top-level standalone functions.
 
 /// Send a message to VRChat chatbox. Address => /chatbox/input s b n
 /// - s: string text
 /// - b: bool (true => send immediately)
 /// - n: bool (true => play sound)
-pub fn send_chatbox_message(_osc_manager: &MaowOscManager, msg: &ChatboxMessage) -> Result<()> {
+pub fn send_chatbox_message(osc_manager: &MaowOscManager, msg: &ChatboxMessage) -> Result<()> {
     // Build the packet
     let osc_msg = OscMessage {
         addr: "/chatbox/input".to_string(),
@@ -49,18 +49,22 @@ pub fn send_chatbox_message(_osc_manager: &MaowOscManager, msg: &ChatboxMessage)
     let packet = OscPacket::Message(osc_msg);
 
     // Reuse the manager's internal logic to send
-    send_packet_to_vrchat(packet)
+    send_packet_to_vrchat(packet)?;
+    osc_manager.counters.record_sent();
+    Ok(())
 }
 
 /// Toggle the chatbox "typing" indicator on or off. Address => /chatbox/typing b
-pub fn set_chatbox_typing(_osc_manager: &MaowOscManager, typing_on: bool) -> Result<()> {
+pub fn set_chatbox_typing(osc_manager: &MaowOscManager, typing_on: bool) -> Result<()> {
     let osc_msg = OscMessage {
         addr: "/chatbox/typing".to_string(),
         args: vec![OscType::Bool(typing_on)],
     };
     let packet = OscPacket::Message(osc_msg);
 
-    send_packet_to_vrchat(packet)
+    send_packet_to_vrchat(packet)?;
+    osc_manager.counters.record_sent();
+    Ok(())
 }
 
 /// Minimal helper that sends the given packet to VRChat's default port (9000).
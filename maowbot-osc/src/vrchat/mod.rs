@@ -9,7 +9,6 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, debug, error};
-use crate::oscquery::client::OscQueryClient;
 use crate::{OscError, Result, VRChatConnectionInfo};
 use crate::oscquery::mdns::service::MdnsService;
 use tokio::time;
@@ -65,7 +64,7 @@ pub fn load_all_vrchat_avatar_configs<P: AsRef<Path>>(dir: P) -> Vec<VrchatAvata
         for entry in entries.flatten() {
             let path = entry.path();
             if let Some(ext) = path.extension() {
-                if ext.to_ascii_lowercase() == "json" {
+                if ext.eq_ignore_ascii_case("json") {
                     match parse_vrchat_avatar_config(&path) {
                         Ok(cfg) => results.push(cfg),
                         Err(e) => {
@@ -38,6 +38,12 @@ pub struct ToggleManager {
     toggles: Vec<SimpleToggle>,
 }
 
+impl Default for ToggleManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ToggleManager {
     pub fn new() -> Self {
         Self { toggles: vec![] }
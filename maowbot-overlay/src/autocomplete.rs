@@ -0,0 +1,159 @@
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use maowbot_common_ui::ChatEvent;
+use maowbot_common_ui::completion::{
+    CompletionConfig, CompletionContext, CompletionEngine, CompletionEngineBuilder, CompletionItem, CompletionScope,
+};
+use maowbot_common_ui::completion::providers::{ChatHistory, ChatHistoryCompletionProvider};
+use std::ffi::CString;
+use std::sync::{Arc, RwLock};
+use crate::ffi::{self, CompletionItemFFI};
+
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Input text and cursor a lookup ran for, and what it found
+type Lookup = (String, usize, Vec<CompletionItem>);
+
+/// Suggests recent chatters after `@` and recently used emotes after `:` in
+/// the chat input. Suggestions show in a popup above the field and are picked
+/// with the laser or Tab.
+pub struct ChatAutocomplete {
+    engine: Arc<CompletionEngine>,
+    history: Arc<RwLock<ChatHistory>>,
+    /// Channel of the latest message, for the completion scope
+    channel: String,
+    input: String,
+    /// Byte offset into `input`
+    cursor: usize,
+    /// What the popup is showing
+    items: Vec<CompletionItem>,
+    results_tx: Sender<Lookup>,
+    results_rx: Receiver<Lookup>,
+}
+
+impl ChatAutocomplete {
+    pub fn new() -> Self {
+        let history = Arc::new(RwLock::new(ChatHistory::new()));
+        let engine = CompletionEngineBuilder::new()
+            .with_config(CompletionConfig {
+                max_items: MAX_SUGGESTIONS,
+                group_by_category: false,
+                ..CompletionConfig::default()
+            })
+            .with_provider(Box::new(ChatHistoryCompletionProvider::new(history.clone())))
+            .build();
+        let (results_tx, results_rx) = unbounded();
+
+        Self {
+            engine: Arc::new(engine),
+            history,
+            channel: String::new(),
+            input: String::new(),
+            cursor: 0,
+            items: Vec::new(),
+            results_tx,
+            results_rx,
+        }
+    }
+
+    pub fn record(&mut self, event: &ChatEvent) {
+        self.history.write().unwrap().record(event);
+        self.channel.clone_from(&event.channel);
+    }
+
+    /// Picks up input edits, finished lookups and a picked suggestion. Call once per frame.
+    pub fn update(&mut self) {
+        if let Some((input, cursor)) = read_chat_input() {
+            self.input = input;
+            self.cursor = cursor;
+            self.request();
+        }
+
+        while let Ok((input, cursor, items)) = self.results_rx.try_recv() {
+            // Drop lookups for text that has changed since
+            if input == self.input && cursor == self.cursor {
+                self.items = items;
+                push_items(&self.items);
+            }
+        }
+
+        let selected = unsafe { ffi::imgui_take_completion_selection() };
+        if let Some(item) = usize::try_from(selected).ok().and_then(|i| self.items.get(i)) {
+            let replacement = item.replacement.clone();
+            self.apply(&replacement);
+        }
+    }
+
+    fn context(&self) -> CompletionContext {
+        let scope = CompletionScope::OverlayChat {
+            platform: "twitch-irc".to_string(),
+            channel: self.channel.clone(),
+        };
+        CompletionContext::new(scope, self.input.clone(), self.cursor)
+    }
+
+    fn request(&mut self) {
+        let context = self.context();
+        if !context.is_mention() && !context.is_emote_shortcode() {
+            if !self.items.is_empty() {
+                self.items.clear();
+                push_items(&[]);
+            }
+            return;
+        }
+
+        // The run loop never yields, so look up on the runtime's other workers
+        let engine = self.engine.clone();
+        let results_tx = self.results_tx.clone();
+        tokio::spawn(async move {
+            let items = engine.get_completions(&context).await;
+            let _ = results_tx.send((context.input, context.cursor_position, items));
+        });
+    }
+
+    fn apply(&mut self, replacement: &str) {
+        let (text, cursor) = self.context().replace_current_word(replacement);
+        let Ok(c_text) = CString::new(text.replace('\0', "")) else { return };
+        unsafe { ffi::imgui_set_chat_input(c_text.as_ptr(), cursor as i32) };
+        // The field reports the new text and cursor next frame
+        self.items.clear();
+    }
+}
+
+/// The input text and cursor if either changed since the last call.
+fn read_chat_input() -> Option<(String, usize)> {
+    let mut buffer = [0u8; 256];
+    let mut cursor = 0i32;
+    if !unsafe { ffi::imgui_get_chat_input(buffer.as_mut_ptr(), buffer.len(), &mut cursor) } {
+        return None;
+    }
+    let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    let input = String::from_utf8_lossy(&buffer[..len]).into_owned();
+
+    let mut cursor = usize::try_from(cursor).unwrap_or(0).min(input.len());
+    while !input.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    Some((input, cursor))
+}
+
+fn push_items(items: &[CompletionItem]) {
+    let ffi_items: Vec<CompletionItemFFI> = items
+        .iter()
+        .map(|item| CompletionItemFFI {
+            display: fixed_str(&item.display),
+            description: fixed_str(item.description.as_deref().unwrap_or("")),
+        })
+        .collect();
+    unsafe { ffi::imgui_update_completions(ffi_items.as_ptr(), ffi_items.len()) };
+}
+
+/// NUL-terminated copy of `text`, cut on a character boundary if too long.
+pub(crate) fn fixed_str(text: &str) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    let mut len = text.len().min(out.len() - 1);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    out[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out
+}
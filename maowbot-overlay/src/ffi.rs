@@ -29,6 +29,10 @@ pub struct OverlaySettingsFFI {
     pub chat_height: f32,
     pub show_alerts: bool,
     pub alert_duration: f32,
+    /// Index into `ControllerButton::ALL`
+    pub keyboard_button: i32,
+    pub dashboard_button: i32,
+    pub trigger_threshold: f32,
 }
 
 #[repr(C)]
@@ -37,6 +41,21 @@ pub struct DashboardState {
     pub current_tab: i32,
 }
 
+/// One row of the chat input's suggestion popup
+#[repr(C)]
+pub struct CompletionItemFFI {
+    pub display: [u8; 64],
+    pub description: [u8; 64],
+}
+
+/// One option of the crowd vote panel
+#[repr(C)]
+pub struct CrowdVoteOptionFFI {
+    pub label: [u8; 64],
+    pub votes: u32,
+    pub weight: u64,
+}
+
 pub type VROverlayHandle = u64;
 
 extern "C" {
@@ -82,6 +101,7 @@ extern "C" {
     pub fn vr_find_hip_tracker() -> u32;
 
     pub fn vr_get_controller_menu_pressed(controller_idx: i32) -> bool;
+    pub fn vr_get_controller_button_pressed(controller_idx: i32, button_id: u32) -> bool;
     pub fn vr_keyboard_init_rendering(device: *mut c_void, context: *mut c_void) -> bool;
     pub fn vr_keyboard_render(
         handle: VROverlayHandle,
@@ -103,10 +123,23 @@ extern "C" {
         input_capacity: usize,
     );
     pub fn imgui_get_sent_message(buffer: *mut u8, capacity: usize) -> bool;
+    pub fn imgui_update_heart_rate(bpm: i32);
+    pub fn imgui_update_crowd_vote(
+        title: *const c_char,
+        options: *const CrowdVoteOptionFFI,
+        count: usize,
+        seconds_left: i32,
+        winner: i32,
+    );
+    pub fn imgui_update_connection_status(state: i32, text: *const c_char);
     pub fn imgui_inject_mouse_pos(x: f32, y: f32);
     pub fn imgui_inject_mouse_button(button: i32, down: bool);
     pub fn imgui_update_laser_state(controller_idx: i32, hit: bool, x: f32, y: f32);
     pub fn imgui_get_input_focused() -> bool;
+    pub fn imgui_get_chat_input(buffer: *mut u8, capacity: usize, cursor: *mut i32) -> bool;
+    pub fn imgui_update_completions(items: *const CompletionItemFFI, count: usize);
+    pub fn imgui_take_completion_selection() -> i32;
+    pub fn imgui_set_chat_input(text: *const c_char, cursor: i32);
     pub fn vr_get_controller_trigger_value(controller_idx: i32) -> f32;
     pub fn vr_wait_get_poses();
     
@@ -114,6 +147,7 @@ extern "C" {
     pub fn imgui_update_dashboard_state(state: *const DashboardState);
     pub fn imgui_update_overlay_settings(settings: *const OverlaySettingsFFI);
     pub fn imgui_get_dashboard_state(state: *mut DashboardState) -> bool;
+    pub fn imgui_take_applied_overlay_settings(settings: *mut OverlaySettingsFFI) -> bool;
 }
 
 // Safe wrappers
@@ -1,7 +1,8 @@
-use maowbot_common_ui::{AppState, ChatState, ChatMessage};
-use maowbot_common_ui::settings::{StreamOverlaySettings, UISettings, AudioSettings};
+use maowbot_common_ui::{AppState, ChatState, ChatMessage, ConnectionStatus, CrowdVoteTally};
+use maowbot_common_ui::settings::{ControllerButton, StreamOverlaySettings, UISettings, AudioSettings};
 use std::ffi::CString;
-use crate::ffi::{DashboardState, OverlaySettingsFFI};
+use crate::autocomplete::fixed_str;
+use crate::ffi::{CrowdVoteOptionFFI, DashboardState, OverlaySettingsFFI};
 
 pub struct ImGuiOverlayRenderer {
     is_dashboard: bool,
@@ -41,6 +42,46 @@ impl ImGuiOverlayRenderer {
         }
     }
 
+    pub fn update_heart_rate(&mut self, bpm: Option<u16>) {
+        unsafe {
+            crate::ffi::imgui_update_heart_rate(bpm.map(i32::from).unwrap_or(0));
+        }
+    }
+
+    /// Live tallies of a crowd vote, or its winner once decided; None hides the panel.
+    pub fn update_crowd_vote(&mut self, vote: Option<&CrowdVoteTally>) {
+        let Some(vote) = vote else {
+            unsafe { crate::ffi::imgui_update_crowd_vote(std::ptr::null(), std::ptr::null(), 0, 0, -1) };
+            return;
+        };
+        let options: Vec<CrowdVoteOptionFFI> = vote.options.iter()
+            .map(|o| CrowdVoteOptionFFI { label: fixed_str(&o.label), votes: o.votes, weight: o.weight })
+            .collect();
+        let title = CString::new(vote.title.replace('\0', "")).unwrap_or_default();
+        unsafe {
+            crate::ffi::imgui_update_crowd_vote(
+                title.as_ptr(),
+                options.as_ptr(),
+                options.len(),
+                vote.seconds_left() as i32,
+                vote.winner.map_or(-1, |w| w as i32),
+            );
+        }
+    }
+
+    /// Shows why the bot is unreachable under the chat title; hidden while connected.
+    pub fn update_connection_status(&mut self, status: &ConnectionStatus) {
+        let state = match status {
+            ConnectionStatus::Connected => 0,
+            ConnectionStatus::Connecting { .. } => 1,
+            ConnectionStatus::Disconnected { .. } => 2,
+        };
+        let text = CString::new(status.to_string().replace('\0', "")).unwrap_or_default();
+        unsafe {
+            crate::ffi::imgui_update_connection_status(state, text.as_ptr());
+        }
+    }
+
     pub fn get_sent_message(&mut self) -> Option<String> {
         self.input_buffer.fill(0);
         let sent = unsafe {
@@ -73,22 +114,38 @@ impl ImGuiOverlayRenderer {
             }
             
             // Convert and send overlay settings
-            let ffi_settings = OverlaySettingsFFI {
-                show_chat: settings.show_chat,
-                chat_opacity: settings.chat_opacity,
-                chat_position_x: settings.chat_position_x,
-                chat_position_y: settings.chat_position_y,
-                chat_width: settings.chat_width,
-                chat_height: settings.chat_height,
-                show_alerts: settings.show_alerts,
-                alert_duration: settings.alert_duration,
-            };
-            
-            unsafe {
-                crate::ffi::imgui_update_overlay_settings(&ffi_settings);
-            }
+            self.push_overlay_settings(settings);
+        }
+    }
+
+    /// Shows `settings` on the dashboard's settings page.
+    pub fn push_overlay_settings(&mut self, settings: &StreamOverlaySettings) {
+        let ffi_settings = overlay_settings_to_ffi(settings);
+        unsafe {
+            crate::ffi::imgui_update_overlay_settings(&ffi_settings);
         }
     }
+
+    /// Copies in the settings from the dashboard if "Apply Settings" was
+    /// pressed since the last call; returns whether it was.
+    pub fn take_applied_overlay_settings(&mut self, settings: &mut StreamOverlaySettings) -> bool {
+        let mut ffi_settings = overlay_settings_to_ffi(settings);
+        if !unsafe { crate::ffi::imgui_take_applied_overlay_settings(&mut ffi_settings) } {
+            return false;
+        }
+        settings.show_chat = ffi_settings.show_chat;
+        settings.chat_opacity = ffi_settings.chat_opacity;
+        settings.chat_position_x = ffi_settings.chat_position_x;
+        settings.chat_position_y = ffi_settings.chat_position_y;
+        settings.chat_width = ffi_settings.chat_width;
+        settings.chat_height = ffi_settings.chat_height;
+        settings.show_alerts = ffi_settings.show_alerts;
+        settings.alert_duration = ffi_settings.alert_duration;
+        settings.controller.keyboard_button = ControllerButton::from_index(ffi_settings.keyboard_button);
+        settings.controller.dashboard_button = ControllerButton::from_index(ffi_settings.dashboard_button);
+        settings.controller.trigger_threshold = ffi_settings.trigger_threshold.clamp(0.05, 0.95);
+        true
+    }
     
     pub fn check_dashboard_state_change(&mut self) -> bool {
         if self.is_dashboard {
@@ -114,4 +171,20 @@ impl ImGuiOverlayRenderer {
     pub fn get_dashboard_state(&self) -> &DashboardState {
         &self.dashboard_state
     }
-}
\ No newline at end of file
+}
+
+fn overlay_settings_to_ffi(settings: &StreamOverlaySettings) -> OverlaySettingsFFI {
+    OverlaySettingsFFI {
+        show_chat: settings.show_chat,
+        chat_opacity: settings.chat_opacity,
+        chat_position_x: settings.chat_position_x,
+        chat_position_y: settings.chat_position_y,
+        chat_width: settings.chat_width,
+        chat_height: settings.chat_height,
+        show_alerts: settings.show_alerts,
+        alert_duration: settings.alert_duration,
+        keyboard_button: settings.controller.keyboard_button.index(),
+        dashboard_button: settings.controller.dashboard_button.index(),
+        trigger_threshold: settings.controller.trigger_threshold,
+    }
+}
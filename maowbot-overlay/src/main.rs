@@ -3,6 +3,7 @@
 mod ffi;
 mod keyboard;
 mod imgui_renderer;
+mod autocomplete;
 
 use anyhow::Result;
 use crossbeam_channel::{bounded, Receiver, Sender};
@@ -12,10 +13,17 @@ use tracing_subscriber::EnvFilter;
 #[cfg(windows)]
 use windows::core::Interface;
 use keyboard::VirtualKeyboard;
-use maowbot_common_ui::{AppEvent, AppState, ChatEvent, SharedGrpcClient};
+use maowbot_common_ui::{AppEvent, AppState, ChatEvent, CrowdVoteTally, SettingsSync, SharedGrpcClient, SharedSettings};
 use imgui_renderer::ImGuiOverlayRenderer;
+use autocomplete::ChatAutocomplete;
 use maowbot_common_ui::events::ChatCommand;
-use maowbot_common_ui::settings::{StreamOverlaySettings, UISettings, AudioSettings};
+use maowbot_common_ui::settings::{ControllerButton, StreamOverlaySettings, UISettings, AudioSettings};
+
+const HEART_RATE_STALE_AFTER: Duration = Duration::from_secs(15);
+/// How long a crowd vote's result stays up after voting closes
+const CROWD_VOTE_RESULT_FOR: Duration = Duration::from_secs(10);
+/// Overlay key of the settings page in the SteamVR dashboard
+const DASHBOARD_OVERLAY_KEY: &str = "maowbot.overlay.dashboard";
 
 struct OverlayApp {
     state: AppState,
@@ -27,11 +35,21 @@ struct OverlayApp {
     show_keyboard: bool,
     hip_tracker_index: Option<u32>,
     renderer: ImGuiOverlayRenderer,
+    /// Mention and emote suggestions for the chat input
+    autocomplete: ChatAutocomplete,
+    /// Latest BPM and when it arrived
+    heart_rate: Option<(u16, Instant)>,
+    /// The crowd vote on screen and when it last changed
+    crowd_vote: Option<(CrowdVoteTally, Instant)>,
     // Settings
     overlay_settings: StreamOverlaySettings,
     ui_settings: UISettings,
     audio_settings: AudioSettings,
     show_settings: bool,
+    /// Saves settings applied on the dashboard so the GUI picks them up too
+    settings_sync: SettingsSync,
+    /// Whether each trigger was past the click threshold last frame
+    trigger_was_down: [bool; 2],
 }
 
 #[cfg(windows)]
@@ -97,6 +115,11 @@ impl OverlayApp {
             command_rx,
         );
 
+        // Follow the settings edited in the GUI, and share the ones applied on the dashboard
+        let url = std::env::var("MAOWBOT_GRPC_URL")
+            .unwrap_or_else(|_| "https://localhost:9999".into());
+        let settings_sync = SettingsSync::start("maowbot-overlay", url, event_tx.clone());
+
         // Create virtual keyboard for HUD mode
         let keyboard = match VirtualKeyboard::new() {
                 Ok(mut kb) => {
@@ -136,10 +159,15 @@ impl OverlayApp {
                 show_keyboard: false,
                 hip_tracker_index: None,
                 renderer: ImGuiOverlayRenderer::new(false),  // HUD renderer
+                autocomplete: ChatAutocomplete::new(),
+                heart_rate: None,
+                crowd_vote: None,
                 overlay_settings: StreamOverlaySettings::default(),
                 ui_settings: UISettings::default(),
                 audio_settings: AudioSettings::default(),
                 show_settings: false,
+                settings_sync,
+                trigger_was_down: [false; 2],
             },
             event_tx,
         ))
@@ -194,6 +222,8 @@ impl OverlayApp {
         // Check for hip tracker periodically
         let mut last_hip_check = Instant::now();
 
+        self.renderer.push_overlay_settings(&self.overlay_settings);
+
         loop {
             // Wait for optimal VR frame timing
             unsafe { ffi::vr_wait_get_poses() };
@@ -211,9 +241,28 @@ impl OverlayApp {
             while let Ok(event) = self.event_rx.try_recv() {
                 match event {
                     AppEvent::Chat(chat_event) => {
+                        self.autocomplete.record(&chat_event);
                         let mut state = self.state.chat_state.lock().unwrap();
                         state.add_message(chat_event);
                     }
+                    AppEvent::HeartRate(bpm) => {
+                        self.heart_rate = bpm.map(|b| (b, Instant::now()));
+                    }
+                    AppEvent::CrowdVote(tally) => {
+                        self.crowd_vote = (tally.status != "cancelled").then(|| (tally, Instant::now()));
+                    }
+                    AppEvent::GrpcStatusChanged(status) => {
+                        self.renderer.update_connection_status(&status);
+                        *self.state.grpc_status.lock().unwrap() = status;
+                    }
+                    AppEvent::SettingsChanged(shared) => match shared {
+                        SharedSettings::Ui(ui) => self.ui_settings = ui,
+                        SharedSettings::Audio(audio) => self.audio_settings = audio,
+                        SharedSettings::Overlay(overlay) => {
+                            self.renderer.push_overlay_settings(&overlay);
+                            self.overlay_settings = overlay;
+                        }
+                    },
                     AppEvent::Shutdown => return Ok(()),
                     _ => {}
                 }
@@ -231,9 +280,26 @@ impl OverlayApp {
 
             // Update ImGui state from Rust
             self.renderer.update_state(&self.state);
+
+            // Hide the heart rate once readings stop arriving
+            let bpm = self.heart_rate
+                .filter(|(_, at)| at.elapsed() < HEART_RATE_STALE_AFTER)
+                .map(|(bpm, _)| bpm);
+            self.renderer.update_heart_rate(bpm);
+
+            // A finished vote shows its result for a while, then goes away
+            if self.crowd_vote.as_ref()
+                .is_some_and(|(tally, at)| !tally.is_open() && at.elapsed() > CROWD_VOTE_RESULT_FOR)
+            {
+                self.crowd_vote = None;
+            }
+            self.renderer.update_crowd_vote(self.crowd_vote.as_ref().map(|(tally, _)| tally));
             
             // Always update overlay settings for dashboard
             self.renderer.update_dashboard_state(true, &self.overlay_settings);
+            if self.renderer.take_applied_overlay_settings(&mut self.overlay_settings) {
+                self.settings_sync.publish(SharedSettings::Overlay(self.overlay_settings.clone()));
+            }
 
             // Process controller input
             self.process_controller_input()?;
@@ -273,6 +339,7 @@ impl OverlayApp {
             if let Some(message) = self.renderer.get_sent_message() {
                 let _ = self.command_tx.send(ChatCommand::SendMessage(message));
             }
+            self.autocomplete.update();
 
             // No manual sleep - let VR compositor handle timing
         }
@@ -280,6 +347,7 @@ impl OverlayApp {
 
     fn process_controller_input(&mut self) -> Result<()> {
         ffi::update_controllers();
+        let bindings = self.overlay_settings.controller.clone();
 
         let mut current_mouse_x = -100.0;
         let mut current_mouse_y = -100.0;
@@ -289,9 +357,15 @@ impl OverlayApp {
             if !unsafe { ffi::vr_get_controller_connected(controller_idx) } {
                 // Clear laser state for disconnected controller
                 unsafe { ffi::imgui_update_laser_state(controller_idx, false, 0.0, 0.0) };
+                self.trigger_was_down[controller_idx as usize] = false;
                 continue;
             }
 
+            let trigger_value = unsafe { ffi::vr_get_controller_trigger_value(controller_idx) };
+            let trigger_pressed = trigger_value > bindings.trigger_threshold;
+            let trigger_clicked = trigger_pressed && !self.trigger_was_down[controller_idx as usize];
+            self.trigger_was_down[controller_idx as usize] = trigger_pressed;
+
             // Test laser intersection with main overlay
             let hit = unsafe { ffi::vr_test_laser_intersection_main(controller_idx) };
 
@@ -310,23 +384,25 @@ impl OverlayApp {
                 current_mouse_y = y;
 
                 // Check if trigger is currently pressed
-                let trigger_value = unsafe { ffi::vr_get_controller_trigger_value(controller_idx) };
-                if trigger_value > 0.5 {
+                if trigger_pressed {
                     trigger_down = true;
                 }
 
                 // Handle trigger press event for haptics
-                if unsafe { ffi::vr_get_controller_trigger_pressed(controller_idx) } {
+                if trigger_clicked {
                     unsafe { ffi::vr_trigger_haptic_pulse(controller_idx, 1000) };
                 }
 
-                // Handle menu button for keyboard toggle
-                if unsafe { ffi::vr_get_controller_menu_pressed(controller_idx) } {
+                // Bound buttons, while pointing at the overlay
+                if button_pressed(controller_idx, bindings.keyboard_button) {
                     self.show_keyboard = !self.show_keyboard;
                     if let Some(ref mut keyboard) = self.keyboard {
                         keyboard.set_visible(self.show_keyboard);
                     }
                 }
+                if button_pressed(controller_idx, bindings.dashboard_button) {
+                    ffi::show_dashboard(DASHBOARD_OVERLAY_KEY);
+                }
             } else {
                 // Clear laser state when not hitting
                 unsafe { ffi::imgui_update_laser_state(controller_idx, false, 0.0, 0.0) };
@@ -381,6 +457,12 @@ impl OverlayApp {
     }
 }
 
+/// Whether `button` went down on the controller this frame; never for a disabled binding.
+fn button_pressed(controller_idx: i32, button: ControllerButton) -> bool {
+    button.openvr_id()
+        .is_some_and(|id| unsafe { ffi::vr_get_controller_button_pressed(controller_idx, id) })
+}
+
 impl Drop for OverlayApp {
     fn drop(&mut self) {
         unsafe {
@@ -5,6 +5,8 @@
 #include <string>
 #include <cstring>
 #include <cfloat>
+#include <cmath>
+#include <cstdio>
 
 #include "imgui.h"
 #include "backends/imgui_impl_dx11.h"
@@ -79,6 +81,45 @@ static std::vector<ChatMessage> g_chat_messages;
 static char g_input_buffer[256] = {0};
 static bool g_message_sent = false;
 
+// ─────────────────────────── Chat Input Completion ──────────────────────
+// Suggestions for the word at the cursor; Rust refreshes them as the input changes
+struct CompletionItemFFI {
+    char display[64];
+    char description[64];
+};
+
+static std::vector<CompletionItemFFI> g_completions;
+static int g_completion_highlight = 0;
+// Suggestion picked with the laser or Tab until Rust takes it, -1 if none
+static int g_completion_selected = -1;
+static bool g_completion_hovered = false;
+// Byte offset of the cursor in g_input_buffer
+static int g_input_cursor = 0;
+// Input with a suggestion applied, written into the text field on the next frame
+static char g_pending_input[256] = {0};
+static int g_pending_cursor = -1;
+static bool g_refocus_input = false;
+
+// Latest heart rate from the bot; 0 hides the widget
+static int g_heart_rate_bpm = 0;
+
+// Crowd control vote from the bot; no options hides the panel
+struct CrowdVoteOptionFFI {
+    char label[64];
+    uint32_t votes;
+    uint64_t weight;
+};
+
+static std::vector<CrowdVoteOptionFFI> g_crowd_vote;
+static char g_crowd_vote_title[128] = {0};
+static int g_crowd_vote_seconds_left = 0;
+// Index of the winning option once decided, -1 while voting
+static int g_crowd_vote_winner = -1;
+
+// Bot connection state (0 connected, 1 connecting, 2 offline) and its description
+static int g_connection_state = 1;
+static char g_connection_text[256] = "Connecting...";
+
 // ─────────────────────────── Dashboard/Settings State ───────────────────
 struct OverlaySettingsFFI {
     bool show_chat;
@@ -89,6 +130,9 @@ struct OverlaySettingsFFI {
     float chat_height;
     bool show_alerts;
     float alert_duration;
+    int keyboard_button;     // index into the controller button list, 0 = disabled
+    int dashboard_button;
+    float trigger_threshold;
 };
 
 struct DashboardState {
@@ -104,9 +148,15 @@ static OverlaySettingsFFI g_overlay_settings = {
     400.0f, // chat_width
     600.0f, // chat_height
     true,   // show_alerts
-    5.0f    // alert_duration
+    5.0f,   // alert_duration
+    1,      // keyboard_button (menu)
+    0,      // dashboard_button (disabled)
+    0.5f    // trigger_threshold
 };
 
+// Set by "Apply Settings" until Rust picks the edited settings up
+static bool g_overlay_settings_applied = false;
+
 static DashboardState g_dashboard_state = {false, 0};
 static bool g_dashboard_state_changed = false;
 
@@ -531,6 +581,18 @@ extern "C" bool vr_get_controller_menu_pressed(int controller_idx) {
     return !was_pressed && is_pressed;
 }
 
+// Rising edge of any button, by EVRButtonId
+extern "C" bool vr_get_controller_button_pressed(int controller_idx, uint32_t button_id) {
+    if (controller_idx < 0 || controller_idx > 1) return false;
+    if (!g_controllers[controller_idx].connected) return false;
+
+    uint64_t mask = ButtonMaskFromId((EVRButtonId)button_id);
+    bool was_pressed = (g_controllers[controller_idx].prev_state.ulButtonPressed & mask) != 0;
+    bool is_pressed = (g_controllers[controller_idx].state.ulButtonPressed & mask) != 0;
+
+    return !was_pressed && is_pressed;
+}
+
 extern "C" bool vr_get_controller_connected(int controller_idx) {
     if (controller_idx < 0 || controller_idx > 1) return false;
     return g_controllers[controller_idx].connected;
@@ -714,6 +776,28 @@ extern "C" void imgui_update_chat_state(const uint8_t* messages_ptr, size_t mess
     }
 }
 
+extern "C" void imgui_update_heart_rate(int bpm) {
+    g_heart_rate_bpm = bpm;
+}
+
+extern "C" void imgui_update_crowd_vote(const char* title, const CrowdVoteOptionFFI* options, size_t count,
+                                        int seconds_left, int winner) {
+    g_crowd_vote.clear();
+    if (options && count > 0) {
+        g_crowd_vote.assign(options, options + count);
+    }
+    strncpy(g_crowd_vote_title, title ? title : "", sizeof(g_crowd_vote_title) - 1);
+    g_crowd_vote_title[sizeof(g_crowd_vote_title) - 1] = 0;
+    g_crowd_vote_seconds_left = seconds_left;
+    g_crowd_vote_winner = winner;
+}
+
+extern "C" void imgui_update_connection_status(int state, const char* text) {
+    g_connection_state = state;
+    strncpy(g_connection_text, text ? text : "", sizeof(g_connection_text) - 1);
+    g_connection_text[sizeof(g_connection_text) - 1] = 0;
+}
+
 extern "C" bool imgui_get_sent_message(uint8_t* buffer, size_t capacity) {
     if (g_message_sent && buffer && capacity > 0) {
         strncpy((char*)buffer, g_input_buffer, capacity - 1);
@@ -725,6 +809,50 @@ extern "C" bool imgui_get_sent_message(uint8_t* buffer, size_t capacity) {
     return false;
 }
 
+extern "C" bool imgui_get_chat_input(uint8_t* buffer, size_t capacity, int* cursor) {
+    static char last_text[256] = {0};
+    static int last_cursor = -1;
+    if (last_cursor == g_input_cursor && strcmp(last_text, g_input_buffer) == 0) {
+        return false;
+    }
+    strncpy(last_text, g_input_buffer, sizeof(last_text) - 1);
+    last_cursor = g_input_cursor;
+
+    if (buffer && capacity > 0) {
+        strncpy((char*)buffer, g_input_buffer, capacity - 1);
+        buffer[capacity - 1] = 0;
+    }
+    if (cursor) {
+        *cursor = g_input_cursor;
+    }
+    return true;
+}
+
+extern "C" void imgui_update_completions(const CompletionItemFFI* items, size_t count) {
+    g_completions.clear();
+    if (items && count > 0) {
+        g_completions.assign(items, items + count);
+    }
+    g_completion_highlight = 0;
+}
+
+extern "C" int imgui_take_completion_selection() {
+    int selected = g_completion_selected;
+    g_completion_selected = -1;
+    return selected;
+}
+
+extern "C" void imgui_set_chat_input(const char* text, int cursor) {
+    strncpy(g_pending_input, text ? text : "", sizeof(g_pending_input) - 1);
+    g_pending_input[sizeof(g_pending_input) - 1] = 0;
+    // Also write the buffer directly in case the field lost focus to the popup
+    strncpy(g_input_buffer, g_pending_input, sizeof(g_input_buffer) - 1);
+    g_input_buffer[sizeof(g_input_buffer) - 1] = 0;
+    g_pending_cursor = cursor;
+    g_refocus_input = true;
+    g_completions.clear();
+}
+
 static void render_settings_window() {
     // For dashboard mode, we want to use the full canvas instead of a window
     ImGuiIO& io = ImGui::GetIO();
@@ -866,9 +994,26 @@ static void render_settings_window() {
                     ImGui::Unindent();
                 }
                 
+                ImGui::Spacing();
+                ImGui::Separator();
+                ImGui::Spacing();
+                
+                // Controller bindings; same order as ControllerButton::ALL on the Rust side
+                {
+                    const char* buttons[] = { "Disabled", "Menu", "Grip", "A / X", "Thumbstick press" };
+                    ImGui::Text("Controller:");
+                    ImGui::Indent();
+                    ImGui::Combo("Keyboard##KeyboardButton", &g_overlay_settings.keyboard_button, buttons, IM_ARRAYSIZE(buttons));
+                    ImGui::Combo("Dashboard##DashboardButton", &g_overlay_settings.dashboard_button, buttons, IM_ARRAYSIZE(buttons));
+                    ImGui::Text("Trigger threshold:");
+                    ImGui::SliderFloat("##TriggerThreshold", &g_overlay_settings.trigger_threshold, 0.05f, 0.95f, "%.2f");
+                    ImGui::Unindent();
+                }
+                
                 ImGui::Spacing();
                 if (ImGui::Button("Apply Settings", ImVec2(150, 40))) {
                     g_dashboard_state_changed = true;
+                    g_overlay_settings_applied = true;
                 }
                 break;
                 
@@ -900,6 +1045,137 @@ static void render_settings_window() {
     ImGui::End();
 }
 
+// Heart rate on the title line, with a dot that pulses once per beat
+static void render_heart_rate_widget() {
+    if (g_heart_rate_bpm <= 0) {
+        return;
+    }
+    char label[32];
+    snprintf(label, sizeof(label), "%d BPM", g_heart_rate_bpm);
+    float dot_radius = ImGui::GetTextLineHeight() * 0.35f;
+    float width = ImGui::CalcTextSize(label).x + dot_radius * 2.0f + ImGui::GetStyle().ItemSpacing.x;
+    ImGui::SameLine(ImGui::GetWindowContentRegionMax().x - width);
+
+    double beat = fmod(ImGui::GetTime() * g_heart_rate_bpm / 60.0, 1.0);
+    float pulse = 1.0f + 0.4f * (float)(beat < 0.15 ? 1.0 - beat / 0.15 : 0.0);
+    ImVec2 pos = ImGui::GetCursorScreenPos();
+    ImVec2 center = ImVec2(pos.x + dot_radius, pos.y + ImGui::GetTextLineHeight() * 0.5f);
+    ImGui::GetWindowDrawList()->AddCircleFilled(center, dot_radius * pulse, IM_COL32(230, 60, 80, 255));
+    ImGui::Dummy(ImVec2(dot_radius * 2.0f, ImGui::GetTextLineHeight()));
+    ImGui::SameLine();
+    ImGui::TextColored(ImVec4(1.0f, 0.45f, 0.5f, 1.0f), "%s", label);
+}
+
+// The crowd vote under the title: a bar per option sized by its share of the
+// vote weight, then the winner once voting closes
+static void render_crowd_vote_panel() {
+    if (g_crowd_vote.empty()) {
+        return;
+    }
+    ImGui::Separator();
+    if (g_crowd_vote_winner >= 0 && g_crowd_vote_winner < (int)g_crowd_vote.size()) {
+        ImGui::TextColored(ImVec4(0.5f, 1.0f, 0.6f, 1.0f), "%s: %s wins!",
+            g_crowd_vote_title, g_crowd_vote[g_crowd_vote_winner].label);
+    } else if (g_crowd_vote_seconds_left > 0) {
+        ImGui::TextColored(ImVec4(1.0f, 0.85f, 0.4f, 1.0f), "%s - %ds left",
+            g_crowd_vote_title, g_crowd_vote_seconds_left);
+    } else {
+        ImGui::TextDisabled("%s - no votes", g_crowd_vote_title);
+    }
+
+    uint64_t total = 0;
+    for (const auto& option : g_crowd_vote) {
+        total += option.weight;
+    }
+    for (int i = 0; i < (int)g_crowd_vote.size(); i++) {
+        const CrowdVoteOptionFFI& option = g_crowd_vote[i];
+        char overlay[96];
+        snprintf(overlay, sizeof(overlay), "#%d %s  (%u)", i + 1, option.label, option.votes);
+        float share = total > 0 ? (float)((double)option.weight / (double)total) : 0.0f;
+        bool winner = i == g_crowd_vote_winner;
+        if (winner) {
+            ImGui::PushStyleColor(ImGuiCol_PlotHistogram, ImVec4(0.3f, 0.8f, 0.4f, 1.0f));
+        }
+        ImGui::ProgressBar(share, ImVec2(-FLT_MIN, 0), overlay);
+        if (winner) {
+            ImGui::PopStyleColor();
+        }
+    }
+}
+
+static int chat_input_callback(ImGuiInputTextCallbackData* data) {
+    switch (data->EventFlag) {
+    case ImGuiInputTextFlags_CallbackCompletion:
+        // Tab accepts the highlighted suggestion
+        if (!g_completions.empty()) {
+            g_completion_selected = g_completion_highlight;
+        }
+        break;
+    case ImGuiInputTextFlags_CallbackHistory:
+        // Up/Down move through the suggestions
+        if (!g_completions.empty()) {
+            int count = (int)g_completions.size();
+            int step = data->EventKey == ImGuiKey_UpArrow ? -1 : 1;
+            g_completion_highlight = (g_completion_highlight + step + count) % count;
+        }
+        break;
+    case ImGuiInputTextFlags_CallbackAlways:
+        // The field keeps its own copy of the text while active, so a picked
+        // suggestion has to be applied through the callback
+        if (g_pending_cursor >= 0) {
+            data->DeleteChars(0, data->BufTextLen);
+            data->InsertChars(0, g_pending_input);
+            int cursor = g_pending_cursor < data->BufTextLen ? g_pending_cursor : data->BufTextLen;
+            data->CursorPos = data->SelectionStart = data->SelectionEnd = cursor;
+            g_pending_cursor = -1;
+        }
+        g_input_cursor = data->CursorPos;
+        break;
+    default:
+        break;
+    }
+    return 0;
+}
+
+// Suggestions stacked above the chat input, with rows tall enough to hit with the laser
+static void render_completion_popup(ImVec2 input_pos) {
+    bool visible = !g_completions.empty() && (g_input_focused || g_completion_hovered);
+    g_completion_hovered = false;
+    if (!visible) {
+        return;
+    }
+
+    const int max_rows = 6;
+    const ImGuiStyle& style = ImGui::GetStyle();
+    int rows = (int)g_completions.size() < max_rows ? (int)g_completions.size() : max_rows;
+    float row_height = ImGui::GetFrameHeight() * 1.5f;
+    float height = rows * (row_height + style.ItemSpacing.y) + style.WindowPadding.y * 2.0f;
+    float width = ImGui::GetContentRegionAvail().x;
+
+    ImVec2 restore = ImGui::GetCursorScreenPos();
+    ImGui::SetCursorScreenPos(ImVec2(input_pos.x, input_pos.y - height - style.ItemSpacing.y));
+    if (ImGui::BeginChild("##Completions", ImVec2(width, height), true)) {
+        for (int i = 0; i < (int)g_completions.size(); i++) {
+            const CompletionItemFFI& item = g_completions[i];
+            ImGui::PushID(i);
+            if (ImGui::Selectable(item.display, i == g_completion_highlight, 0, ImVec2(0, row_height))) {
+                g_completion_selected = i;
+            }
+            if (ImGui::IsItemHovered()) {
+                g_completion_highlight = i;
+            }
+            if (item.description[0]) {
+                ImGui::SameLine(width * 0.55f);
+                ImGui::TextDisabled("%s", item.description);
+            }
+            ImGui::PopID();
+        }
+        g_completion_hovered = ImGui::IsWindowHovered();
+    }
+    ImGui::EndChild();
+    ImGui::SetCursorScreenPos(restore);
+}
+
 static void render_chat_window(bool is_dashboard) {
     ImGui::SetNextWindowPos(ImVec2(10, 10), ImGuiCond_FirstUseEver);
     ImGui::SetNextWindowSize(ImVec2(1004, 748), ImGuiCond_FirstUseEver);
@@ -912,6 +1188,14 @@ static void render_chat_window(bool is_dashboard) {
 
     // Title
     ImGui::TextColored(ImVec4(0.7f, 0.9f, 1.0f, 1.0f), "maowbot Chat");
+    render_heart_rate_widget();
+    if (g_connection_state != 0) {
+        ImVec4 color = g_connection_state == 1
+            ? ImVec4(1.0f, 0.8f, 0.2f, 1.0f)
+            : ImVec4(1.0f, 0.35f, 0.35f, 1.0f);
+        ImGui::TextColored(color, "%s", g_connection_text);
+    }
+    render_crowd_vote_panel();
     ImGui::Separator();
 
     // Chat area
@@ -931,8 +1215,12 @@ static void render_chat_window(bool is_dashboard) {
 
     // Input
     ImGui::Separator();
+    render_completion_popup(ImGui::GetCursorScreenPos());
     bool reclaim_focus = false;
-    ImGuiInputTextFlags input_flags = ImGuiInputTextFlags_EnterReturnsTrue;
+    ImGuiInputTextFlags input_flags = ImGuiInputTextFlags_EnterReturnsTrue |
+        ImGuiInputTextFlags_CallbackAlways |
+        ImGuiInputTextFlags_CallbackCompletion |
+        ImGuiInputTextFlags_CallbackHistory;
 
     // Check if input is about to be focused
     bool was_focused = g_input_focused;
@@ -940,7 +1228,13 @@ static void render_chat_window(bool is_dashboard) {
     // Push a custom ID to ensure we can track this specific input
     ImGui::PushID("ChatInput");
 
-    if (ImGui::InputText("##Input", g_input_buffer, sizeof(g_input_buffer), input_flags)) {
+    if (g_refocus_input) {
+        // Picking a suggestion with the laser takes focus off the field
+        ImGui::SetKeyboardFocusHere();
+        g_refocus_input = false;
+    }
+
+    if (ImGui::InputText("##Input", g_input_buffer, sizeof(g_input_buffer), input_flags, chat_input_callback)) {
         if (strlen(g_input_buffer) > 0) {
             g_message_sent = true;
             reclaim_focus = true;
@@ -1148,4 +1442,13 @@ extern "C" bool imgui_get_dashboard_state(DashboardState* state) {
         return true;
     }
     return false;
+}
+
+extern "C" bool imgui_take_applied_overlay_settings(OverlaySettingsFFI* settings) {
+    if (settings && g_overlay_settings_applied) {
+        *settings = g_overlay_settings;
+        g_overlay_settings_applied = false;
+        return true;
+    }
+    return false;
 }
\ No newline at end of file
@@ -122,6 +122,9 @@ struct OverlaySettingsFFI {
     float chat_height;
     bool show_alerts;
     float alert_duration;
+    int keyboard_button;     // index into the controller button list, 0 = disabled
+    int dashboard_button;
+    float trigger_threshold;
 };
 
 struct DashboardState {
@@ -144,9 +147,15 @@ static OverlaySettingsFFI g_overlay_settings = {
     400.0f, // chat_width
     600.0f, // chat_height
     true,   // show_alerts
-    5.0f    // alert_duration
+    5.0f,   // alert_duration
+    1,      // keyboard_button (menu)
+    0,      // dashboard_button (disabled)
+    0.5f    // trigger_threshold
 };
 
+// Set by "Apply Settings" until Rust picks the edited settings up
+static bool g_overlay_settings_applied = false;
+
 static DashboardState g_dashboard_state = {false, 0};
 static bool g_dashboard_state_changed = false;
 
@@ -283,6 +292,10 @@ extern "C" bool vr_get_controller_menu_pressed(int controller_idx) {
     return false;
 }
 
+extern "C" bool vr_get_controller_button_pressed(int controller_idx, uint32_t button_id) {
+    return false;
+}
+
 extern "C" LaserHit vr_test_laser_intersection(int controller_idx, VROverlayHandle_t handle) {
     LaserHit result = {false, 0, 0, FLT_MAX};
     return result;
@@ -361,6 +374,19 @@ extern "C" void imgui_update_chat_state(const uint8_t* messages_ptr, size_t mess
     // Don't clear them when updating from Rust
 }
 
+extern "C" void imgui_update_heart_rate(int bpm) {
+    // No-op in stub
+}
+
+extern "C" void imgui_update_crowd_vote(const char* title, const void* options, size_t count,
+                                        int seconds_left, int winner) {
+    // No-op in stub
+}
+
+extern "C" void imgui_update_connection_status(int state, const char* text) {
+    // No-op in stub
+}
+
 extern "C" bool imgui_get_sent_message(uint8_t* buffer, size_t capacity) {
     if (g_message_sent && buffer && capacity > 0) {
         strncpy((char*)buffer, g_input_buffer, capacity - 1);
@@ -379,6 +405,26 @@ extern "C" bool imgui_get_sent_message(uint8_t* buffer, size_t capacity) {
     return false;
 }
 
+extern "C" bool imgui_get_chat_input(uint8_t* buffer, size_t capacity, int* cursor) {
+    // The stub input never changes outside of sending
+    return false;
+}
+
+extern "C" void imgui_update_completions(const void* items, size_t count) {
+    if (count > 0) {
+        std::cout << "[STUB] " << count << " completion suggestions\n";
+    }
+}
+
+extern "C" int imgui_take_completion_selection() {
+    return -1;
+}
+
+extern "C" void imgui_set_chat_input(const char* text, int cursor) {
+    strncpy(g_input_buffer, text ? text : "", sizeof(g_input_buffer) - 1);
+    g_input_buffer[sizeof(g_input_buffer) - 1] = 0;
+}
+
 extern "C" void imgui_update_laser_state(int controller_idx, bool hit, float x, float y) {
     // No-op in stub
 }
@@ -534,8 +580,14 @@ static void render_settings_window() {
             ImGui::Checkbox("Show Alerts", &g_overlay_settings.show_alerts);
             ImGui::SliderFloat("Alert Duration", &g_overlay_settings.alert_duration, 1.0f, 30.0f);
             
+            ImGui::Separator();
+            
+            // Controller bindings
+            ImGui::SliderFloat("Trigger Threshold", &g_overlay_settings.trigger_threshold, 0.05f, 0.95f);
+            
             if (ImGui::Button("Apply Settings")) {
                 g_dashboard_state_changed = true;
+                g_overlay_settings_applied = true;
                 std::cout << "[STUB] Settings applied\n";
             }
             
@@ -597,6 +649,15 @@ extern "C" bool imgui_get_dashboard_state(DashboardState* state) {
     return false;
 }
 
+extern "C" bool imgui_take_applied_overlay_settings(OverlaySettingsFFI* settings) {
+    if (settings && g_overlay_settings_applied) {
+        *settings = g_overlay_settings;
+        g_overlay_settings_applied = false;
+        return true;
+    }
+    return false;
+}
+
 // Additional overlay functions
 extern "C" bool vr_create_overlays() {
     std::cout << "[STUB] Creating overlays\n";
@@ -21,6 +21,21 @@ fn main() {
         "proto/services/autostart_service.proto",
         "proto/services/obs_service.proto",
         "proto/services/event_pipeline_service.proto",
+        "proto/services/chat_archive_service.proto",
+        "proto/services/giveaway_service.proto",
+        "proto/services/crowd_vote_service.proto",
+        "proto/services/protection_service.proto",
+        "proto/services/moderation_rules_service.proto",
+        "proto/services/emote_stats_service.proto",
+        "proto/services/emote_set_service.proto",
+        "proto/services/localization_service.proto",
+        "proto/services/event_stream_service.proto",
+        "proto/services/ui_settings_service.proto",
+        "proto/services/responder_service.proto",
+        "proto/services/alerting_service.proto",
+        "proto/services/health_service.proto",
+        "proto/services/stream_session_service.proto",
+        "proto/services/export_service.proto",
     ];
     
     protos.extend(service_protos);
@@ -28,6 +43,9 @@ fn main() {
     tonic_build::configure()
         .build_server(true)
         .build_client(true)
+        // Chat messages carry badges, emotes and reply context, which would
+        // make every other payload as large as they are
+        .boxed(".plugs.PluginStreamResponse.payload.chat_message")
         .compile_protos(&protos, &["proto"])
         .unwrap();
 }
\ No newline at end of file
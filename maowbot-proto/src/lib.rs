@@ -16,5 +16,12 @@ pub mod maowbot {
     }
 }
 
+/// gRPC metadata header selecting the workspace (name or UUID) a request applies to.
+/// Requests without it use the default workspace.
+pub const WORKSPACE_METADATA_KEY: &str = "x-maowbot-workspace";
+
+/// gRPC metadata header carrying the client's API token as "Bearer <token>".
+pub const AUTHORIZATION_METADATA_KEY: &str = "authorization";
+
 // Re-export prost_types for convenience
 pub use prost_types;
\ No newline at end of file
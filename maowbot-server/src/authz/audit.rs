@@ -0,0 +1,108 @@
+// What a state-changing call changed. The authz layer puts an `AuditNote` in
+// the extensions of every such request; the service fills it in (target and
+// before/after values, and `WorkspaceResolver` the workspace it acted on) and
+// the layer writes it to the audit log together with the caller and how the
+// call ended.
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use tonic::Request;
+use uuid::Uuid;
+
+use maowbot_common::models::api_token::AuditLogEntry;
+
+#[derive(Debug, Default)]
+struct AuditChange {
+    target: String,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+#[derive(Debug, Default)]
+struct Noted {
+    change: Option<AuditChange>,
+    workspace: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditNote(Arc<Mutex<Noted>>);
+
+impl AuditNote {
+    /// The note for `request`. Take it before `into_inner()`; a request the
+    /// layer didn't audit gets a detached note that is never written.
+    pub fn of<T>(request: &Request<T>) -> Self {
+        request.extensions().get::<AuditNote>().cloned().unwrap_or_default()
+    }
+
+    /// Records what the call changed. A later call replaces an earlier one.
+    pub fn change(&self, target: impl Into<String>, before: Option<Value>, after: Option<Value>) {
+        let change = AuditChange { target: target.into(), before, after };
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).change = Some(change);
+    }
+
+    /// Records the workspace the call acted on, once it's been resolved.
+    pub fn workspace(&self, workspace_id: Uuid) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).workspace = Some(workspace_id);
+    }
+
+    /// Copies the noted change and workspace into `entry`.
+    pub(super) fn fill(&self, entry: &mut AuditLogEntry) {
+        let mut noted = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(workspace_id) = noted.workspace.take() {
+            entry.workspace = Some(workspace_id.to_string());
+        }
+        if let Some(change) = noted.change.take() {
+            entry.target = Some(change.target);
+            entry.before_value = change.before;
+            entry.after_value = change.after;
+        }
+    }
+}
+
+/// Serializes a value for the audit log; None if it can't be.
+pub fn audit_value<T: serde::Serialize>(value: &T) -> Option<Value> {
+    serde_json::to_value(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    #[test]
+    fn test_services_fill_in_the_layers_note() {
+        let note = AuditNote::default();
+        let mut request = Request::new(());
+        request.extensions_mut().insert(note.clone());
+
+        AuditNote::of(&request).change("config:chat.prefix", Some(json!("!")), Some(json!("?")));
+        let brand = Uuid::new_v4();
+        AuditNote::of(&request).workspace(brand);
+
+        let mut entry = AuditLogEntry {
+            audit_id: 0,
+            token_id: None,
+            client_name: Some("tui".to_string()),
+            role: Some("admin".to_string()),
+            method: "/maowbot.services.ConfigService/SetConfig".to_string(),
+            workspace: None,
+            allowed: true,
+            reason: None,
+            created_at: Utc::now(),
+            target: None,
+            before_value: None,
+            after_value: None,
+            outcome: Some("Ok".to_string()),
+        };
+        note.fill(&mut entry);
+        assert_eq!(entry.target.as_deref(), Some("config:chat.prefix"));
+        assert_eq!(entry.before_value, Some(json!("!")));
+        assert_eq!(entry.after_value, Some(json!("?")));
+        assert_eq!(entry.workspace, Some(brand.to_string()));
+
+        // Requests the layer didn't audit get a note nobody reads
+        AuditNote::of(&Request::new(())).change("ignored", None, None);
+    }
+}
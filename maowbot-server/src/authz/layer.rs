@@ -0,0 +1,241 @@
+// Tower layer that authenticates and authorizes every gRPC request before it
+// reaches a service, and audits the calls that can change state once they
+// finish. tonic interceptors can't see which method is being called, so this
+// sits in front of the router instead. Tokens limited to a workspace only get
+// through to methods that stay in it (see `permissions`).
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use chrono::Utc;
+use http::{Request, Response};
+use tonic::body::BoxBody;
+use tonic::{Code, Status};
+use tower::{Layer, Service};
+
+use maowbot_common::models::api_token::{AuditLogEntry, Permission};
+use maowbot_proto::AUTHORIZATION_METADATA_KEY;
+
+use super::audit::AuditNote;
+use super::permissions::{required_permission, split_path, stays_in_workspace};
+use super::tokens::TokenStore;
+use super::Caller;
+
+#[derive(Clone)]
+pub struct AuthzLayer {
+    tokens: TokenStore,
+}
+
+impl AuthzLayer {
+    pub fn new(tokens: TokenStore) -> Self {
+        Self { tokens }
+    }
+}
+
+impl<S> Layer<S> for AuthzLayer {
+    type Service = AuthzService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthzService { inner, tokens: self.tokens.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthzService<S> {
+    inner: S,
+    tokens: TokenStore,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for AuthzService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        // Use the instance that was polled ready and leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let tokens = self.tokens.clone();
+
+        // Read everything needed up front: the body isn't Sync, so `req` can't be borrowed across an await
+        let path = req.uri().path().to_string();
+        let header = |name: &str| req.headers().get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let secret = header(AUTHORIZATION_METADATA_KEY)
+            .and_then(|v| v.strip_prefix("Bearer ").map(str::to_string));
+
+        Box::pin(async move {
+            match authorize(&tokens, path, secret).await {
+                Ok(Some(Authorized { caller, audit: None })) => {
+                    req.extensions_mut().insert(caller);
+                    inner.call(req).await
+                }
+                Ok(Some(Authorized { caller, audit: Some(mut entry) })) => {
+                    let note = AuditNote::default();
+                    req.extensions_mut().insert(caller);
+                    req.extensions_mut().insert(note.clone());
+                    let response = inner.call(req).await;
+                    entry.outcome = Some(match &response {
+                        Ok(response) => outcome(response),
+                        Err(_) => "TransportError".to_string(),
+                    });
+                    note.fill(&mut entry);
+                    tokens.audit(entry);
+                    response
+                }
+                Ok(None) => inner.call(req).await,
+                Err(status) => Ok(status.into_http()),
+            }
+        })
+    }
+}
+
+struct Authorized {
+    caller: Caller,
+    /// The audit entry to complete and write once the call finishes; set for
+    /// every method that needs more than read permission
+    audit: Option<AuditLogEntry>,
+}
+
+/// How a call ended, from the response's gRPC status. Errors come back as
+/// trailers-only responses with the status in the headers; successful calls
+/// send it in the trailers.
+fn outcome(response: &Response<BoxBody>) -> String {
+    match response.headers().get("grpc-status") {
+        Some(code) => format!("{:?}", Code::from_bytes(code.as_bytes())),
+        None => format!("{:?}", Code::Ok),
+    }
+}
+
+/// Checks the request's token against the method's required permission, and
+/// a workspace token against the methods that stay in its workspace. Returns
+/// `Ok(None)` for services that handle authentication themselves.
+///
+/// Audit entries start out with the token's workspace; the one the call acted
+/// on is noted once `WorkspaceResolver` has checked it. The client's
+/// workspace header isn't trusted for either.
+async fn authorize(
+    tokens: &TokenStore,
+    path: String,
+    secret: Option<String>,
+) -> Result<Option<Authorized>, Status> {
+    let Some((service, method)) = split_path(&path) else {
+        return Err(Status::unimplemented(format!("Unknown method {}", path)));
+    };
+    let Some(required) = required_permission(service, method) else {
+        return Ok(None);
+    };
+
+    let token = match &secret {
+        Some(secret) => tokens.authenticate(secret).await,
+        None => None,
+    };
+    let Some(token) = token else {
+        let reason = if secret.is_some() { "invalid or revoked API token" } else { "missing API token" };
+        tokens.audit(AuditLogEntry {
+            audit_id: 0,
+            token_id: None,
+            client_name: None,
+            role: None,
+            method: path.clone(),
+            workspace: None,
+            allowed: false,
+            reason: Some(reason.to_string()),
+            created_at: Utc::now(),
+            target: None,
+            before_value: None,
+            after_value: None,
+            outcome: None,
+        });
+        return Err(Status::unauthenticated(format!("{} for {}", reason, path)));
+    };
+
+    // The reason written to the audit log, and the message for the client
+    let denial = if !token.role.grants(required) {
+        Some((
+            format!("requires {} permission", required),
+            format!("Token '{}' ({}) lacks {} permission for {}", token.name, token.role, required, path),
+        ))
+    } else {
+        token.workspace_id.filter(|_| !stays_in_workspace(service, method)).map(|workspace_id| {
+            let reason = format!("limited to workspace {}; {} acts on the whole server", workspace_id, path);
+            let message = format!("Token '{}' is {}", token.name, reason);
+            (reason, message)
+        })
+    };
+    let entry = AuditLogEntry {
+        audit_id: 0,
+        token_id: Some(token.token_id),
+        client_name: Some(token.name.clone()),
+        role: Some(token.role.to_string()),
+        method: path.clone(),
+        workspace: token.workspace_id.map(|id| id.to_string()),
+        allowed: denial.is_none(),
+        reason: denial.as_ref().map(|(reason, _)| reason.clone()),
+        created_at: Utc::now(),
+        target: None,
+        before_value: None,
+        after_value: None,
+        outcome: None,
+    };
+    if let Some((_, message)) = denial {
+        tokens.audit(entry);
+        return Err(Status::permission_denied(message));
+    }
+
+    Ok(Some(Authorized {
+        caller: Caller { name: token.name, role: token.role, workspace_id: token.workspace_id },
+        audit: (required != Permission::Read).then_some(entry),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maowbot_common::models::api_token::ApiRole;
+    use maowbot_core::crypto::Encryptor;
+    use maowbot_core::repositories::{memory, Repositories};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_workspace_tokens_only_reach_workspace_methods() {
+        let db = memory::open_database().await.unwrap();
+        let repos = Repositories::memory(&db, Encryptor::new(&[7u8; 32]).unwrap());
+        let tokens = TokenStore::load(repos.api_tokens.clone()).await.unwrap();
+        let brand = Uuid::new_v4();
+        let (_, scoped) = tokens.create("brand-mod", ApiRole::Moderator, Some(brand)).await.unwrap();
+        let (_, global) = tokens.create("mod", ApiRole::Moderator, None).await.unwrap();
+
+        let call = |path: &str, secret: &str| authorize(&tokens, path.to_string(), Some(secret.to_string()));
+
+        // Global services are refused, reads included
+        for path in [
+            "/maowbot.services.UserService/AddUserNote",
+            "/maowbot.services.ChatArchiveService/SearchMessages",
+            "/maowbot.services.TwitchService/GetFollowers",
+            "/maowbot.services.CommandService/StreamCommandEvents",
+        ] {
+            let denied = call(path, &scoped).await.err().unwrap();
+            assert_eq!(denied.code(), Code::PermissionDenied, "{}", path);
+            assert!(call(path, &global).await.unwrap().is_some(), "{}", path);
+        }
+
+        // Workspace methods go through, audited under the token's workspace
+        let allowed = call("/maowbot.services.CommandService/CreateCommand", &scoped).await.unwrap().unwrap();
+        assert_eq!(allowed.caller.workspace_id, Some(brand));
+        assert_eq!(allowed.audit.unwrap().workspace, Some(brand.to_string()));
+        let allowed = call("/maowbot.services.CommandService/CreateCommand", &global).await.unwrap().unwrap();
+        assert_eq!(allowed.audit.unwrap().workspace, None);
+    }
+}
@@ -0,0 +1,29 @@
+//! maowbot-server/src/authz/mod.rs
+//!
+//! Role-based access control for the gRPC services. Clients send an API token
+//! (`authorization: Bearer <token>`); each token is bound to a role (admin,
+//! moderator, readonly) and each service method requires a permission. Every
+//! call that needs more than read permission is recorded in the audit log once
+//! it finishes, with what it changed when the service notes it (see
+//! `AuditNote`), as is every denied call.
+
+pub mod audit;
+pub mod layer;
+pub mod permissions;
+pub mod tokens;
+
+use maowbot_common::models::api_token::ApiRole;
+use uuid::Uuid;
+
+pub use audit::{audit_value, AuditNote};
+pub use layer::AuthzLayer;
+pub use tokens::TokenStore;
+
+/// The authenticated client, available to services via `request.extensions()`.
+#[derive(Debug, Clone)]
+pub struct Caller {
+    pub name: String,
+    pub role: ApiRole,
+    /// The only workspace the caller's token may use; None for every workspace.
+    pub workspace_id: Option<Uuid>,
+}
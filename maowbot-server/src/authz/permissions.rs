@@ -0,0 +1,529 @@
+// Required permission for every gRPC method.
+//
+// Each service lists the methods that need less than its default; anything
+// not listed (including methods added later and unknown services) requires
+// `Permission::Admin`, so forgetting an entry fails closed.
+//
+// Tokens limited to a workspace may only call the methods listed as staying in
+// the caller's workspace (they go through `WorkspaceResolver`); everything
+// else acts on the whole server. That fails closed the same way.
+
+use maowbot_common::models::api_token::Permission;
+use Permission::{Admin, Moderate, Read};
+
+use crate::startup::HEALTH_SERVICE;
+
+/// Services that bypass token checks. The legacy plugin service authenticates
+/// plugins with the plugin passphrase; the health service only reports startup
+/// readiness, which clients check before they have anything else to do.
+const EXEMPT_SERVICES: &[&str] = &["plugs.PluginService", HEALTH_SERVICE];
+
+struct ServicePermissions {
+    service: &'static str,
+    default: Permission,
+    /// Methods that only touch the workspace `WorkspaceResolver` picks
+    workspace: &'static [&'static str],
+    methods: &'static [(&'static str, Permission)],
+}
+
+const SERVICES: &[ServicePermissions] = &[
+    ServicePermissions {
+        service: "maowbot.services.ConfigService",
+        default: Admin,
+        workspace: &[
+            "GetConfig",
+            "SetConfig",
+            "DeleteConfig",
+            "ListConfigs",
+            "BatchGetConfigs",
+            "BatchSetConfigs",
+            "ExportConfigs",
+            "ImportConfigs",
+        ],
+        methods: &[
+            ("GetConfig", Read),
+            ("ListConfigs", Read),
+            ("BatchGetConfigs", Read),
+            ("ValidateConfig", Read),
+            ("GetConfigHistory", Read),
+            ("StreamConfigUpdates", Read),
+            ("ListWorkspaces", Read),
+            ("ListWorkspaceChannels", Read),
+            ("GetLogLevels", Read),
+            ("GetSettingsSchema", Read),
+            ("GetUpdateStatus", Read),
+            ("CheckForUpdate", Read),
+            ("GetDatabaseStatus", Read),
+            ("CheckDatabaseIntegrity", Read),
+            ("GetCertificateInfo", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.CredentialService",
+        default: Admin,
+        workspace: &[
+            // Stores nothing; CompleteAuthFlow resolves the workspace
+            "BeginAuthFlow",
+            "CompleteAuthFlow",
+            "ListCredentials",
+            "RefreshCredential",
+            "GetCredential",
+            "StoreCredential",
+            "RevokeCredential",
+            "BatchRefreshCredentials",
+            "BatchListCredentials",
+            "GetCredentialHealth",
+            "BatchValidateCredentials",
+        ],
+        methods: &[
+            ("GetCredentialHealth", Read),
+            ("GetScopeStatus", Read),
+            ("ListChatIdentities", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.CommandService",
+        default: Moderate,
+        workspace: &[
+            "ListCommands",
+            "GetCommand",
+            "CreateCommand",
+            "UpdateCommand",
+            "DeleteCommand",
+            "BatchListCommands",
+            "BatchUpdateCommands",
+            "ExecuteCommand",
+            "TestCommand",
+            "GetCommandUsage",
+            "GetCommandStats",
+            "ImportCommands",
+        ],
+        methods: &[
+            ("ListCommands", Read),
+            ("GetCommand", Read),
+            ("BatchListCommands", Read),
+            ("GetCommandUsage", Read),
+            ("GetCommandStats", Read),
+            ("StreamCommandEvents", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.RedeemService",
+        default: Moderate,
+        workspace: &[
+            "ListRedeems",
+            "GetRedeem",
+            "CreateRedeem",
+            "UpdateRedeem",
+            "DeleteRedeem",
+            "BatchListRedeems",
+            "BatchUpdateRedeems",
+            "SyncRedeems",
+            "GetSyncStatus",
+            "ExecuteRedeem",
+            "TestRedeem",
+            "GetRedeemUsage",
+        ],
+        methods: &[
+            ("ListRedeems", Read),
+            ("GetRedeem", Read),
+            ("BatchListRedeems", Read),
+            ("GetSyncStatus", Read),
+            ("GetRedeemUsage", Read),
+            ("StreamRedeemEvents", Read),
+            ("ListRedeemSchedules", Read),
+            ("ListRedeemApprovals", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.UserService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("GetUser", Read),
+            ("ListUsers", Read),
+            ("SearchUsers", Read),
+            ("FindUserByName", Read),
+            ("BatchGetUsers", Read),
+            ("GetPlatformIdentities", Read),
+            ("GetUserAnalysis", Read),
+            ("GetAnalysisRecomputeStatus", Read),
+            ("StreamUserUpdates", Read),
+            ("UpdateUser", Moderate),
+            ("AddRoleToIdentity", Moderate),
+            ("RemoveRoleFromIdentity", Moderate),
+            ("UpdateUserAnalysis", Moderate),
+            ("AppendModeratorNote", Moderate),
+            // Notes and moderation history are for mods only, not dashboards
+            ("AddUserNote", Moderate),
+            ("ListUserNotes", Moderate),
+            ("DeleteUserNote", Moderate),
+            ("RecordModerationAction", Moderate),
+            ("ListModerationHistory", Moderate),
+            // The card includes notes, so it's mod-only too
+            ("GetViewerCard", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.ChatArchiveService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("GetLastSeen", Read),
+            ("ListRetentionPolicies", Read),
+            ("GetChatActivity", Read),
+            ("SearchMessages", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.GiveawayService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("ListGiveaways", Read),
+            ("GetGiveaway", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.CrowdVoteService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("GetVote", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.ProtectionService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("GetProtectionStatus", Read),
+            ("ListIncidents", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.ModerationRulesService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("ListRules", Read),
+            ("TestMessage", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.ResponderService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("ListResponders", Read),
+            ("TestResponders", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.AlertingService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("ListAlertRules", Read),
+            ("ListRecentAlerts", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.EmoteStatsService",
+        default: Read,
+        workspace: &[],
+        methods: &[],
+    },
+    ServicePermissions {
+        service: "maowbot.services.EmoteSetService",
+        default: Read,
+        workspace: &[],
+        methods: &[
+            ("RefreshChannelEmotes", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.StreamSessionService",
+        default: Read,
+        workspace: &[],
+        methods: &[],
+    },
+    // Whole chat logs, so the same bar as the rest of the archive
+    ServicePermissions {
+        service: "maowbot.services.ExportService",
+        default: Admin,
+        workspace: &[],
+        methods: &[],
+    },
+    ServicePermissions {
+        service: "maowbot.services.LocalizationService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("ListLanguages", Read),
+            ("ListMessages", Read),
+            ("ListChannelLanguages", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.EventStreamService",
+        default: Read,
+        workspace: &[],
+        methods: &[],
+    },
+    ServicePermissions {
+        service: "maowbot.services.UiSettingsService",
+        default: Read,
+        workspace: &[],
+        methods: &[
+            ("UpdateUiSettings", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.TwitchService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("JoinChannel", Admin),
+            ("PartChannel", Admin),
+            ("GetJoinedChannels", Read),
+            ("GetChannelInfo", Read),
+            ("GetStreamInfo", Read),
+            ("GetFollowers", Read),
+            ("GetFollowAge", Read),
+            ("GetSubscribers", Read),
+            ("CheckSubscription", Read),
+            ("GetChannelPointRewards", Read),
+            ("StreamTwitchEvents", Read),
+            ("ListVodChapters", Read),
+            ("GetVodChapters", Read),
+            ("ListScheduleSegments", Read),
+            ("ListEventSubSubscriptions", Read),
+            ("RepairEventSubSubscriptions", Admin),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.DiscordService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("ListGuilds", Read),
+            ("GetGuild", Read),
+            ("ListChannels", Read),
+            ("GetChannel", Read),
+            ("ListRoles", Read),
+            ("GetMember", Read),
+            ("ListMembers", Read),
+            ("ListEventConfigs", Read),
+            ("ListLiveRoles", Read),
+            ("StreamDiscordEvents", Read),
+            ("SendMessage", Moderate),
+            ("EditMessage", Moderate),
+            ("DeleteMessage", Moderate),
+            ("SendEmbed", Moderate),
+            ("AddRoleToUser", Moderate),
+            ("RemoveRoleFromUser", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot_proto.services.event_pipeline.EventPipelineService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("GetPipeline", Read),
+            ("ListPipelines", Read),
+            ("ListFilters", Read),
+            ("ListActions", Read),
+            ("GetAvailableFilters", Read),
+            ("GetAvailableActions", Read),
+            ("GetExecutionHistory", Read),
+            ("GetExecutionDetails", Read),
+            ("ListSimulatedEvents", Read),
+            ("GetPipelineThrottle", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.OBSService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("ConfigureInstance", Admin),
+            ("ListInstances", Read),
+            ("GetInstanceStatus", Read),
+            ("ListScenes", Read),
+            ("ListSources", Read),
+            ("GetStreamStatus", Read),
+            ("GetRecordingStatus", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.OSCService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("StartOSC", Admin),
+            ("StopOSC", Admin),
+            ("RestartOSC", Admin),
+            ("SendRawOSC", Admin),
+            ("StreamOSCPackets", Admin),
+            ("ImportDripPack", Admin),
+            ("CreateMidiMapping", Admin),
+            ("SetMidiMappingEnabled", Admin),
+            ("DeleteMidiMapping", Admin),
+            ("GetOSCStatus", Read),
+            ("DiscoverPeers", Read),
+            ("GetPeerInfo", Read),
+            ("GetAvatarParameters", Read),
+            ("ListTriggers", Read),
+            ("ListTriggersWithRedeems", Read),
+            ("ListActiveToggles", Read),
+            ("ExportDripPack", Read),
+            ("PreviewDripPack", Read),
+            ("ListMidiMappings", Read),
+            ("StreamOSCEvents", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.VRChatService",
+        default: Moderate,
+        workspace: &[],
+        methods: &[
+            ("GetCurrentUser", Read),
+            ("GetCurrentWorld", Read),
+            ("GetWorld", Read),
+            ("GetCurrentInstance", Read),
+            ("GetCurrentAvatar", Read),
+            ("GetAvatar", Read),
+            ("ListAvatars", Read),
+            ("GetAvatarParameters", Read),
+            ("ListFriends", Read),
+            ("GetFriend", Read),
+            ("ListNotifications", Read),
+            ("StreamVRChatEvents", Read),
+            ("ListVRChatSessions", Read),
+            ("GetVRChatPresence", Read),
+            ("ListVRChatGroupRequests", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.AIService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("GetAIStatus", Read),
+            ("ListProviders", Read),
+            ("ListFunctions", Read),
+            ("GetSystemPrompt", Read),
+            ("ListSystemPrompts", Read),
+            ("GetMemory", Read),
+            ("SearchMemories", Read),
+            ("GetContext", Read),
+            ("GetAIUsage", Read),
+            ("GetModelPerformance", Read),
+            ("GenerateChat", Moderate),
+            ("StreamGenerateChat", Moderate),
+            ("CallFunction", Moderate),
+            ("SetSystemPrompt", Moderate),
+            ("CreateMemory", Moderate),
+            ("UpdateMemory", Moderate),
+            ("DeleteMemory", Moderate),
+            ("CreateContext", Moderate),
+            ("UpdateContext", Moderate),
+            ("ClearContext", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.PlatformService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("GetPlatformConfig", Read),
+            ("ListPlatformConfigs", Read),
+            ("GetPlatformRuntimeStatus", Read),
+            ("ListActiveRuntimes", Read),
+            ("GetPlatformCapabilities", Read),
+            ("StreamPlatformEvents", Read),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.PluginService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("ListPlugins", Read),
+            ("GetPlugin", Read),
+            ("GetPluginCapabilities", Read),
+            ("GetSystemStatus", Read),
+            ("GetRuntimeMetrics", Read),
+            ("RunDiagnostics", Read),
+            ("StreamPluginMessages", Read),
+            ("SendPluginMessage", Moderate),
+        ],
+    },
+    ServicePermissions {
+        service: "maowbot.services.AutostartService",
+        default: Admin,
+        workspace: &[],
+        methods: &[
+            ("ListAutostartEntries", Read),
+            ("IsAutostartEnabled", Read),
+        ],
+    },
+];
+
+/// Splits a gRPC request path ("/package.Service/Method") into service and method.
+pub fn split_path(path: &str) -> Option<(&str, &str)> {
+    let mut parts = path.trim_start_matches('/').splitn(2, '/');
+    let service = parts.next().filter(|s| !s.is_empty())?;
+    let method = parts.next().filter(|m| !m.is_empty() && !m.contains('/'))?;
+    Some((service, method))
+}
+
+/// The permission needed to call `method` on `service`, or `None` for exempt services.
+pub fn required_permission(service: &str, method: &str) -> Option<Permission> {
+    if EXEMPT_SERVICES.contains(&service) {
+        return None;
+    }
+    let Some(table) = SERVICES.iter().find(|s| s.service == service) else {
+        return Some(Admin);
+    };
+    Some(
+        table.methods.iter()
+            .find(|(name, _)| *name == method)
+            .map(|(_, perm)| *perm)
+            .unwrap_or(table.default),
+    )
+}
+
+/// Whether a token limited to one workspace may call `method` on `service`.
+pub fn stays_in_workspace(service: &str, method: &str) -> bool {
+    SERVICES.iter()
+        .find(|s| s.service == service)
+        .is_some_and(|table| table.workspace.contains(&method))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_permission() {
+        assert_eq!(split_path("/maowbot.services.ConfigService/SetConfig"),
+                   Some(("maowbot.services.ConfigService", "SetConfig")));
+        assert_eq!(split_path("/not-grpc"), None);
+
+        assert_eq!(required_permission("maowbot.services.ConfigService", "GetConfig"), Some(Read));
+        assert_eq!(required_permission("maowbot.services.ConfigService", "ShutdownServer"), Some(Admin));
+        assert_eq!(required_permission("maowbot.services.CommandService", "CreateCommand"), Some(Moderate));
+        assert_eq!(required_permission("maowbot.services.TwitchService", "JoinChannel"), Some(Admin));
+        assert_eq!(required_permission("maowbot.services.UnknownService", "Anything"), Some(Admin));
+        assert_eq!(required_permission("plugs.PluginService", "StartSession"), None);
+        assert_eq!(required_permission("maowbot.services.HealthService", "GetReadiness"), None);
+
+        assert!(stays_in_workspace("maowbot.services.CommandService", "CreateCommand"));
+        assert!(!stays_in_workspace("maowbot.services.CommandService", "StreamCommandEvents"));
+        assert!(!stays_in_workspace("maowbot.services.ConfigService", "ShutdownServer"));
+        assert!(!stays_in_workspace("maowbot.services.UserService", "AddUserNote"));
+        assert!(!stays_in_workspace("maowbot.services.UnknownService", "Anything"));
+    }
+}
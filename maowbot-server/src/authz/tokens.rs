@@ -0,0 +1,255 @@
+// API tokens: generation, hashing, the in-memory lookup cache used on every
+// request, the local admin token bootstrap, and audit log writes.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use maowbot_common::models::api_token::{ApiRole, ApiToken, AuditLogEntry};
+use maowbot_common::traits::repository_traits::ApiTokenRepository;
+use maowbot_core::crypto::secrets::generate_key;
+use maowbot_core::Error;
+
+/// Name of the admin token the server creates for local clients.
+pub const LOCAL_ADMIN_TOKEN_NAME: &str = "local-admin";
+const TOKEN_PREFIX: &str = "mwb_";
+/// `last_used_at` is written at most this often per token.
+const TOUCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A fresh random token secret: "mwb_" followed by 32 random bytes, base64url-encoded.
+pub fn generate_secret() -> Result<String, Error> {
+    Ok(format!("{}{}", TOKEN_PREFIX, URL_SAFE_NO_PAD.encode(generate_key()?)))
+}
+
+/// Hex SHA-256 of a token secret, as stored in `api_tokens.token_hash`.
+pub fn hash_secret(secret: &str) -> String {
+    Sha256::digest(secret.trim().as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Where the local admin token is written (mode 0600).
+pub fn local_admin_token_file() -> Result<PathBuf, Error> {
+    dirs::config_dir()
+        .map(|dir| dir.join("maowbot").join("admin.token"))
+        .ok_or_else(|| Error::Auth("Could not determine config directory".to_string()))
+}
+
+/// Active tokens keyed by hash, shared by the authz layer and the config service.
+#[derive(Clone)]
+pub struct TokenStore {
+    repo: Arc<dyn ApiTokenRepository>,
+    active: Arc<RwLock<HashMap<String, ApiToken>>>,
+    last_touched: Arc<RwLock<HashMap<Uuid, Instant>>>,
+}
+
+impl TokenStore {
+    pub async fn load(repo: Arc<dyn ApiTokenRepository>) -> Result<Self, Error> {
+        let store = Self {
+            repo,
+            active: Arc::new(RwLock::new(HashMap::new())),
+            last_touched: Arc::new(RwLock::new(HashMap::new())),
+        };
+        store.reload().await?;
+        Ok(store)
+    }
+
+    pub fn repo(&self) -> &Arc<dyn ApiTokenRepository> {
+        &self.repo
+    }
+
+    /// Re-reads active tokens from the database. Call after creating or revoking one.
+    pub async fn reload(&self) -> Result<(), Error> {
+        let tokens = self.repo.list_tokens().await?;
+        let active = tokens.into_iter()
+            .filter(|t| t.revoked_at.is_none())
+            .map(|t| (t.token_hash.clone(), t))
+            .collect();
+        *self.active.write().await = active;
+        Ok(())
+    }
+
+    /// The active token matching `secret`, if any.
+    pub async fn authenticate(&self, secret: &str) -> Option<ApiToken> {
+        let token = self.active.read().await.get(&hash_secret(secret)).cloned()?;
+        self.touch(token.token_id).await;
+        Some(token)
+    }
+
+    async fn touch(&self, token_id: Uuid) {
+        {
+            let touched = self.last_touched.read().await;
+            if touched.get(&token_id).is_some_and(|t| t.elapsed() < TOUCH_INTERVAL) {
+                return;
+            }
+        }
+        self.last_touched.write().await.insert(token_id, Instant::now());
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.touch_token(token_id, Utc::now()).await {
+                warn!("Failed to update last use of API token {}: {}", token_id, e);
+            }
+        });
+    }
+
+    /// Creates a token and returns it with its secret, which is not stored anywhere.
+    /// A token given a workspace can only use that workspace, and can't be an admin
+    /// token: admins manage workspaces and tokens themselves.
+    pub async fn create(&self, name: &str, role: ApiRole, workspace_id: Option<Uuid>) -> Result<(ApiToken, String), Error> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::ValidationError("Token name cannot be empty".into()));
+        }
+        if workspace_id.is_some() && role == ApiRole::Admin {
+            return Err(Error::ValidationError("An admin token cannot be limited to one workspace".into()));
+        }
+        if self.repo.get_token_by_name(name).await?.is_some() {
+            return Err(Error::ValidationError(format!("A token named '{}' already exists", name)));
+        }
+        let secret = generate_secret()?;
+        let token = ApiToken {
+            token_id: Uuid::new_v4(),
+            name: name.to_string(),
+            role,
+            token_hash: hash_secret(&secret),
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+            workspace_id,
+        };
+        self.repo.create_token(&token).await?;
+        self.reload().await?;
+        Ok((token, secret))
+    }
+
+    pub async fn revoke(&self, name: &str) -> Result<ApiToken, Error> {
+        let token = self.repo.get_token_by_name(name).await?
+            .filter(|t| t.revoked_at.is_none())
+            .ok_or_else(|| Error::NotFound(format!("No active token named '{}'", name)))?;
+        self.repo.revoke_token(token.token_id).await?;
+        self.reload().await?;
+        Ok(token)
+    }
+
+    /// Makes sure the local admin token file holds a valid admin token, so clients on
+    /// this machine (TUI, GUI) can connect without setup. Re-issues it if the file is
+    /// missing or its token was revoked.
+    pub async fn ensure_local_admin_token(&self) -> Result<(), Error> {
+        let path = local_admin_token_file()?;
+        if let Ok(secret) = std::fs::read_to_string(&path) {
+            if let Some(token) = self.active.read().await.get(&hash_secret(&secret)) {
+                if token.role == ApiRole::Admin {
+                    return Ok(());
+                }
+            }
+        }
+
+        let secret = generate_secret()?;
+        match self.repo.get_token_by_name(LOCAL_ADMIN_TOKEN_NAME).await? {
+            Some(existing) if existing.role == ApiRole::Admin => {
+                self.repo.reset_token_hash(existing.token_id, &hash_secret(&secret)).await?;
+            }
+            Some(existing) => {
+                return Err(Error::Auth(format!(
+                    "Cannot create the local admin token: a '{}' token with role '{}' already exists",
+                    existing.name, existing.role
+                )));
+            }
+            None => {
+                self.repo.create_token(&ApiToken {
+                    token_id: Uuid::new_v4(),
+                    name: LOCAL_ADMIN_TOKEN_NAME.to_string(),
+                    role: ApiRole::Admin,
+                    token_hash: hash_secret(&secret),
+                    created_at: Utc::now(),
+                    last_used_at: None,
+                    revoked_at: None,
+                    workspace_id: None,
+                }).await?;
+            }
+        }
+        write_token_file(&path, &secret)?;
+        self.reload().await?;
+        info!("Wrote local admin API token to {}", path.display());
+        Ok(())
+    }
+
+    /// Writes an audit entry in the background; failures are only logged.
+    pub fn audit(&self, entry: AuditLogEntry) {
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.insert_audit_entry(&entry).await {
+                warn!("Failed to write audit log entry for {}: {}", entry.method, e);
+            }
+        });
+    }
+}
+
+fn write_token_file(path: &Path, secret: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A leftover temp file is removed first: its permissions can't be trusted.
+    let tmp = path.with_extension("tmp");
+    match std::fs::remove_file(&tmp) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        // Owner-only from the moment it exists, never readable by others
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(secret.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_hashing() {
+        let secret = generate_secret().unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(hash_secret(&secret), hash_secret(&format!("{}\n", secret)));
+        assert_ne!(hash_secret(&secret), hash_secret(&generate_secret().unwrap()));
+        assert_eq!(hash_secret("abc").len(), 64);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_token_file_is_owner_only_over_leftover_temp() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("maowbot-token-{}", Uuid::new_v4()));
+        let path = dir.join("admin.token");
+        std::fs::create_dir_all(&dir).unwrap();
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, "stale").unwrap();
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_token_file(&path, "mwb_secret").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "mwb_secret");
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert!(!tmp.exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -4,13 +4,16 @@
 
 use std::time::Duration;
 use tokio::{sync::mpsc, time};
-use tonic::transport::{Channel, ClientTlsConfig, Certificate};
+use tonic::transport::{Channel, ClientTlsConfig, Certificate, Identity};
 use futures_util::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{info, error};
 use std::fs;
 
 use maowbot_core::Error;
+use maowbot_proto::maowbot::services::{
+    health_service_client::HealthServiceClient, GetReadinessRequest, ReadinessState,
+};
 use maowbot_proto::plugs::{
     plugin_service_client::PluginServiceClient,
     PluginStreamRequest,
@@ -29,13 +32,21 @@ pub async fn run_client(args: Args) -> Result<(), Error> {
     // Load CA from local "certs/server.crt"
     let ca_cert_pem = fs::read("certs/server.crt")?;
     let ca_cert = Certificate::from_pem(ca_cert_pem);
-    let tls_config = ClientTlsConfig::new().ca_certificate(ca_cert);
+    let mut tls_config = ClientTlsConfig::new().ca_certificate(ca_cert);
+    // Needed when the server requires mutual TLS (tls.require_client_cert)
+    if let (Ok(cert), Ok(key)) = (fs::read(crate::tls::LOCAL_CLIENT_CERT_PATH), fs::read(crate::tls::LOCAL_CLIENT_KEY_PATH)) {
+        tls_config = tls_config.identity(Identity::from_pem(cert, key));
+    }
 
     let channel = Channel::from_shared(server_url)?
         .tls_config(tls_config)?
         .connect()
         .await?;
 
+    if let Some(secs) = args.wait_ready {
+        wait_until_ready(channel.clone(), Duration::from_secs(secs)).await?;
+    }
+
     let mut client = PluginServiceClient::new(channel);
 
     let (tx, rx) = mpsc::channel::<PluginStreamRequest>(20);
@@ -75,10 +86,6 @@ pub async fn run_client(args: Args) -> Result<(), Error> {
                     RespPayload::GameEvent(ge) => {
                         println!("GameEvent => {}: {}", ge.name, ge.json);
                     },
-                    // Fallback if prost adds more variants in future:
-                    _ => {
-                        println!("Received unknown plugin response variant.");
-                    }
                 }
             }
         }
@@ -109,3 +116,41 @@ pub async fn run_client(args: Args) -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Polls the health service until the server has finished starting.
+/// Servers that predate it count as ready.
+async fn wait_until_ready(channel: Channel, timeout: Duration) -> Result<(), Error> {
+    let mut health = HealthServiceClient::new(channel);
+    let deadline = time::Instant::now() + timeout;
+    let mut last_waiting = String::new();
+    loop {
+        let waiting = match health.get_readiness(GetReadinessRequest {}).await {
+            Ok(resp) => {
+                let readiness = resp.into_inner();
+                if readiness.ready {
+                    return Ok(());
+                }
+                let up = [ReadinessState::Ready as i32, ReadinessState::Degraded as i32];
+                match readiness.subsystems.iter().find(|s| !up.contains(&s.state)) {
+                    Some(s) if s.state == ReadinessState::Failed as i32 => {
+                        return Err(Error::Internal(format!("The server failed to start {}: {}", s.name, s.message)));
+                    }
+                    Some(s) => format!("waiting on {}", s.name),
+                    None => "finishing".to_string(),
+                }
+            }
+            Err(status) if status.code() == tonic::Code::Unimplemented => return Ok(()),
+            Err(status) => status.message().to_string(),
+        };
+        if waiting != last_waiting {
+            info!("Waiting for the server to finish starting ({})", waiting);
+            last_waiting = waiting;
+        }
+        if time::Instant::now() >= deadline {
+            return Err(Error::Internal(format!(
+                "The server did not finish starting within {}s ({})", timeout.as_secs(), last_waiting
+            )));
+        }
+        time::sleep(Duration::from_millis(500)).await;
+    }
+}
@@ -2,11 +2,15 @@
 //!
 //! Defines the main "global" context (ServerContext) for the bot server.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
-use maowbot_core::db::Database;
+use maowbot_core::db::{Database, DbBackend, DbConnection};
+use maowbot_core::db::sqlite::SqliteDatabase;
+use maowbot_core::repositories::{memory, Repositories};
 use maowbot_core::eventbus::{EventBus, db_logger_handle::DbLoggerControl};
 use maowbot_core::crypto::Encryptor;
+use maowbot_core::crypto::secrets::SecretsManager;
 use maowbot_core::services::{message_service::MessageService, user_service::UserService, EventSubService};
 use maowbot_core::services::twitch::{
     command_service::CommandService,
@@ -15,145 +19,227 @@ use maowbot_core::services::twitch::{
 use maowbot_core::services::event_pipeline_service::EventPipelineService;
 use maowbot_core::platforms::manager::PlatformManager;
 use maowbot_core::plugins::manager::PluginManager;
+use maowbot_core::settings::SettingsRegistry;
+use maowbot_core::updater::Updater;
 use maowbot_core::Error;
-use base64::Engine;
 
 use crate::Args;
+use crate::db_maintenance::{self, DbMaintenance};
 use crate::portable_postgres::*;
+use crate::startup::{Startup, Subsystem};
 use tracing::{info, error, warn};
-use rand::{thread_rng, Rng, RngCore};
-use keyring::Entry;
-use base64;
-use maowbot_common::models::cache::{CacheConfig, TrimPolicy};
-use maowbot_common::traits::repository_traits::*;
+use maowbot_common::models::cache::{channel_key, CacheConfig, ChannelRetention, TrimPolicy};
+use maowbot_common::models::user::{User, UserListQuery};
 use maowbot_core::auth::manager::AuthManager;
 use maowbot_core::auth::user_manager::DefaultUserManager;
 use maowbot_core::cache::message_cache::ChatCache;
-use maowbot_core::repositories::postgres::analytics::PostgresAnalyticsRepository;
-use maowbot_core::repositories::postgres::autostart::{PostgresAutostartRepository, AutostartRepository};
-use maowbot_core::repositories::postgres::bot_config::PostgresBotConfigRepository;
-use maowbot_core::repositories::postgres::command_usage::PostgresCommandUsageRepository;
-use maowbot_core::repositories::postgres::commands::PostgresCommandRepository;
-use maowbot_core::repositories::postgres::credentials::PostgresCredentialsRepository;
-use maowbot_core::repositories::postgres::drip::DripRepository;
-use maowbot_core::repositories::postgres::discord::PostgresDiscordRepository;
-use maowbot_core::repositories::postgres::platform_config::PostgresPlatformConfigRepository;
-use maowbot_core::repositories::postgres::platform_identity::PlatformIdentityRepository;
-use maowbot_core::repositories::postgres::redeem_usage::PostgresRedeemUsageRepository;
-use maowbot_core::repositories::postgres::redeems::PostgresRedeemRepository;
-use maowbot_core::repositories::postgres::user::UserRepository;
-use maowbot_core::repositories::postgres::user_analysis::PostgresUserAnalysisRepository;
-use maowbot_core::repositories::postgres::obs::PostgresObsRepository;
-use maowbot_core::repositories::postgres::event_pipeline::PostgresEventPipelineRepository;
+use maowbot_core::services::donations::DonationService;
+use maowbot_core::services::heart_rate::HeartRateService;
+use maowbot_core::services::midi::MidiService;
+use maowbot_core::services::twitch::stream_marker_service::StreamMarkerService;
+use maowbot_core::services::twitch::giveaway_service::GiveawayService;
+use maowbot_core::services::twitch::crowd_vote_service::CrowdVoteService;
+use maowbot_core::services::twitch::protection_service::ProtectionService;
+use maowbot_core::services::twitch::bot_detection::BotDetectionService;
+use maowbot_core::services::twitch::chatter_presence::ChatterPresenceService;
+use maowbot_core::services::twitch::schedule_service::ScheduleService;
+use maowbot_core::services::twitch::hype_detector::HypeDetector;
+use maowbot_core::services::twitch::milestone_service::MilestoneService;
+use maowbot_core::services::twitch::stream_session_service::StreamSessionService;
+use maowbot_core::services::responders::ResponderService;
+use maowbot_core::services::alerting::AlertingService;
+use maowbot_core::services::emote_stats::EmoteStatsService;
+use maowbot_core::services::emote_sets::EmoteSetService;
+use maowbot_core::services::twitch::clip_service::ClipService;
+use maowbot_core::services::twitch::redeem_schedule_service::RedeemScheduleService;
+use maowbot_core::services::twitch::redeem_approval_service::RedeemApprovalService;
+use maowbot_core::services::vrchat_session_service::VRChatSessionService;
+use maowbot_core::services::vrchat_presence_service::VRChatPresenceService;
+use maowbot_core::services::osc_chat_relay::OscChatRelayService;
+use maowbot_core::services::vrchat_group_service::VRChatGroupService;
+use maowbot_core::services::viewer_card::ViewerCardService;
+use maowbot_core::services::outbound_guard::OutboundGuard;
+use maowbot_core::services::outbound_chain::OutboundChain;
+use maowbot_core::i18n::Localizer;
+use maowbot_core::services::moderation::ModerationService;
+use maowbot_core::tasks::analysis_recompute::AnalysisRecompute;
+use maowbot_core::tasks::retention::RetentionEngine;
+use maowbot_core::services::ui_events::UiEventStream;
+use maowbot_core::services::ui_settings::UiSettingsStore;
+use maowbot_core::services::twitch::scope_check::ScopeCheckService;
+use maowbot_core::platforms::twitch::routing::TwitchAccountRouter;
+use maowbot_core::services::chat_identity::ChatIdentityRouter;
+use maowbot_core::services::workspace_router::WorkspaceRouter;
+use maowbot_core::services::ai_safety::AiResponseShaper;
+use maowbot_core::services::chat_summarizer::ChatSummarizer;
 use maowbot_osc::MaowOscManager;
 use maowbot_osc::oscquery::OscQueryServer;
 use maowbot_osc::robo::RoboControlSystem;
-use maowbot_osc::vrchat::AvatarWatcher;
+
+/// How often the chat cache drops spammy users' messages and enforces its caps.
+const CHAT_CACHE_TRIM_INTERVAL_SECS: u64 = 60;
+
+/// How often bot_config is re-read for changes made outside the settings registry.
+pub const SETTINGS_RELOAD_INTERVAL_SECS: u64 = 10;
 
 /// The global server context (a bag of references to DB, event bus, plugin manager, etc.).
 pub struct ServerContext {
-    pub db: Database,
+    /// Startup progress of each subsystem, reported by the health service.
+    pub startup: Arc<Startup>,
+    pub db: DbConnection,
     pub event_bus: Arc<EventBus>,
     pub auth_manager: Arc<Mutex<AuthManager>>,
     pub message_service: Arc<MessageService>,
     pub platform_manager: Arc<PlatformManager>,
     pub plugin_manager: Arc<PluginManager>,
     pub eventsub_service: Arc<EventSubService>,
+    #[allow(dead_code)]
     pub command_service: Arc<CommandService>,
     pub redeem_service: Arc<RedeemService>,
     pub event_pipeline_service: Arc<EventPipelineService>,
+    /// Typed, hot-reloaded bot_config settings.
+    pub settings: Arc<SettingsRegistry>,
+    /// Release checks, verified binary swaps and update restarts.
+    pub updater: Arc<Updater>,
+    /// Snapshots, compaction and integrity checks.
+    pub db_maintenance: Arc<DbMaintenance>,
+    /// Age and size limits for chat, the event journal, command usage and AI memories.
+    pub retention: Arc<RetentionEngine>,
+    /// Streamlabs/StreamElements tip ingestion.
+    pub donation_service: Arc<DonationService>,
+    /// Pulsoid/HypeRate heart rate, forwarded to OSC and the overlay.
+    pub heart_rate_service: Arc<HeartRateService>,
+    /// MIDI notes and controllers mapped to OSC parameters, OBS scenes and chat macros.
+    pub midi_service: Arc<MidiService>,
+    /// Automatic Twitch stream markers and per-VOD chapter export.
+    pub stream_marker_service: Arc<StreamMarkerService>,
+    /// Keyword/channel point giveaways with eligibility rules and weighted draws.
+    pub giveaway_service: Arc<GiveawayService>,
+    /// Chat votes among OSC effects, weighted by watchtime.
+    pub crowd_vote_service: Arc<CrowdVoteService>,
+    /// Raid defense: spike detection, chat lockdowns and Shield Mode.
+    pub protection_service: Arc<ProtectionService>,
+    pub bot_detection_service: Arc<BotDetectionService>,
+    /// Link, phrase and regex moderation rules enforced in the broadcaster's chat.
+    pub moderation_service: Arc<ModerationService>,
+    /// Emote usage per channel in rolling windows and daily totals.
+    pub emote_stats_service: Arc<EmoteStatsService>,
+    /// 7TV, BTTV and FFZ emote sets of the watched channels, kept in sync.
+    pub emote_set_service: Arc<EmoteSetService>,
+    /// The resumable event feed UI clients subscribe to.
+    pub ui_events: Arc<UiEventStream>,
+    /// GUI and overlay settings, shared so a change in one shows up in the other.
+    pub ui_settings: Arc<UiSettingsStore>,
+    /// Missing scopes on stored Twitch tokens and the features they degrade.
+    pub scope_check: Arc<ScopeCheckService>,
+    /// Which account speaks in each channel, for multi-bot setups.
+    pub chat_identities: Arc<ChatIdentityRouter>,
+    /// Which workspace's commands, redeems and config answer in each channel.
+    pub workspace_router: Arc<WorkspaceRouter>,
+    /// New clips posted to Discord, and `!clipit`.
+    pub clip_service: Arc<ClipService>,
+    /// Bot responses in each channel's language.
+    pub localizer: Arc<Localizer>,
+    /// Time, category and hype train driven reward pricing and availability.
+    pub redeem_schedule_service: Arc<RedeemScheduleService>,
+    /// Redemptions waiting for the streamer to approve or deny them.
+    pub redeem_approval_service: Arc<RedeemApprovalService>,
+    /// VRChat session checks and keep-alive.
+    pub vrchat_session_service: Arc<VRChatSessionService>,
+    /// Friends in the streamer's VRChat instance, join/leave events and `!whosHere`.
+    pub vrchat_presence_service: Arc<VRChatPresenceService>,
+    /// Viewers in the broadcaster's Twitch chat: join/leave events, `!lurkers` and watchtime.
+    pub chatter_presence_service: Arc<ChatterPresenceService>,
+    /// The Twitch stream schedule: `!schedule`, editing and starting-soon actions.
+    pub schedule_service: Arc<ScheduleService>,
+    /// Chat, emote, bits and sub spikes: markers, replay clips and hype events.
+    pub hype_detector: Arc<HypeDetector>,
+    /// Sub anniversaries, sub streaks and follow anniversaries, celebrated once each.
+    pub milestone_service: Arc<MilestoneService>,
+    /// One session per broadcast with per-stream stats, for "this stream" vs "all time".
+    pub stream_session_service: Arc<StreamSessionService>,
+    /// Regex and keyword chat responders, separate from prefix commands.
+    pub responder_service: Arc<ResponderService>,
+    /// Ops alerts (disconnects, failing credentials and pipelines, low disk) routed by alert rules.
+    pub alerting_service: Arc<AlertingService>,
+    /// Mentions, mod messages and highlights relayed to the VRChat chatbox.
+    pub osc_chat_relay: Arc<OscChatRelayService>,
+    /// VRChat group join requests and going-live announcements.
+    pub vrchat_group_service: Arc<VRChatGroupService>,
+    /// One viewer's identities, standing, notes, chat and AI memory together.
+    pub viewer_card_service: Arc<ViewerCardService>,
+    /// On-demand, resumable recompute of every user's analysis scores.
+    pub analysis_recompute: Arc<AnalysisRecompute>,
+    /// Periodic per-channel chat summaries kept in the AI's memory.
+    pub chat_summarizer: Arc<ChatSummarizer>,
 
-    /// The raw references in case you need them.
-    pub creds_repo: Arc<PostgresCredentialsRepository>,
-    pub bot_config_repo: Arc<PostgresBotConfigRepository>,
-    pub autostart_repo: Arc<dyn AutostartRepository + Send + Sync>,
-    pub command_repo: Arc<dyn CommandRepository + Send + Sync>,
-    pub command_usage_repo: Arc<dyn CommandUsageRepository + Send + Sync>,
-    pub redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
-    pub redeem_usage_repo: Arc<dyn RedeemUsageRepository + Send + Sync>,
-    pub obs_repo: Arc<PostgresObsRepository>,
+    /// Master key storage and the shared encryptor used by every repository holding secrets.
+    pub secrets: Arc<Mutex<SecretsManager>>,
+    pub encryptor: Encryptor,
+
+    /// Every repository, over whichever backend `db` is.
+    pub repos: Repositories,
 
     pub osc_manager: Arc<MaowOscManager>,
+    // Held so they live as long as the server; nothing reads them back yet
+    #[allow(dead_code)]
     pub robo_control: Arc<tokio::sync::Mutex<RoboControlSystem>>,
+    #[allow(dead_code)]
     pub oscquery_server: Arc<tokio::sync::Mutex<OscQueryServer>>,
+    #[allow(dead_code)]
     pub ai_service: Option<Arc<maowbot_ai::plugins::ai_service::AiService>>,
-    pub analytics_repo: Arc<dyn AnalyticsRepo + Send + Sync>,
     pub db_logger_control: Option<DbLoggerControl>,
 }
 
 impl ServerContext {
-    /// Creates and configures the entire context for "server" mode.
-    pub async fn new(args: &Args) -> Result<Self, Error> {
-        // 1) Start local Postgres (if needed)
-        let pg_bin_dir = "./postgres/bin";
-        let pg_data_dir = "./postgres/data";
-        let port = 5432;
-
-        if let Err(e) = kill_leftover_postgres_if_any(pg_bin_dir, pg_data_dir) {
-            error!("Failed to handle leftover Postgres: {:?}", e);
-        }
-
-        ensure_db_initialized(pg_bin_dir, pg_data_dir)?;
-        start_postgres(pg_bin_dir, pg_data_dir, port)?;
-        create_database(pg_bin_dir, port, "maowbot")?;
+    /// Creates and configures the entire context for "server" mode, marking
+    /// the database and repositories ready in `startup` along the way.
+    pub async fn new(args: &Args, startup: Arc<Startup>) -> Result<Self, Error> {
+        // 1) Open the database (the bundled or an external Postgres, a SQLite file, or memory)
+        startup.begin(Subsystem::Database).map_err(Error::Internal)?;
+        let OpenedDb { db, data_dir_check, applied } = if args.memory_db {
+            open_memory().await?
+        } else {
+            match DbBackend::from_url(&args.db_path)? {
+                DbBackend::Postgres => open_postgres(args).await?,
+                DbBackend::Sqlite => open_sqlite(args).await?,
+            }
+        };
+        let migrated = match applied {
+            0 => String::new(),
+            n => format!("applied {} migration(s)", n),
+        };
+        startup.ready(Subsystem::Database, migrated);
 
-        // 2) Connect to DB
-        let db_url = &args.db_path;
-        info!("Using Postgres DB URL: {}", db_url);
-        let db = Database::new(db_url).await?;
-        
-        // Check if we should nuke the database and start fresh
-        if args.nuke_database_and_start_fresh {
-            info!("--nuke-database-and-start-fresh flag detected. Dropping all tables...");
-            
-            // Execute DROP commands for all tables
-            sqlx::query(
-                r#"
-                DO $$ DECLARE
-                    r RECORD;
-                BEGIN
-                    FOR r IN (SELECT tablename FROM pg_tables WHERE schemaname = 'public') LOOP
-                        EXECUTE 'DROP TABLE IF EXISTS ' || quote_ident(r.tablename) || ' CASCADE';
-                    END LOOP;
-                END $$;
-                "#
-            )
-            .execute(db.pool())
-            .await?;
-            
-            info!("Database nuked successfully. Rebuilding from scratch...");
-        }
-        
-        // Apply migrations (this will recreate tables if they were dropped)
-        db.migrate().await?;
+        // 2) Build the repositories over it
+        startup.begin(Subsystem::Repositories).map_err(Error::Internal)?;
+        let secrets = SecretsManager::load_or_create()?;
+        let encryptor = Encryptor::new(secrets.key())?;
+        let repos = match &db {
+            DbConnection::Postgres(pg) => Repositories::postgres(pg, encryptor.clone()),
+            DbConnection::Sqlite(lite) => Repositories::sqlite(lite, encryptor.clone()),
+            DbConnection::Memory(mem) => Repositories::memory(mem, encryptor.clone()),
+        };
 
         // Possibly create an owner user if users table is empty
-        maybe_create_owner_user(&db).await?;
-
-        // 3) Build core repos
-        let encryptor = Encryptor::new(&get_master_key()?)?;
-        let creds_repo_arc = Arc::new(PostgresCredentialsRepository::new(db.pool().clone(), encryptor.clone()));
-        let platform_config_repo = Arc::new(PostgresPlatformConfigRepository::new(db.pool().clone()));
-        let bot_config_repo = Arc::new(
-            PostgresBotConfigRepository::new(db.pool().clone())
-        );
-        let analytics_repo = Arc::new(PostgresAnalyticsRepository::new(db.pool().clone()));
-        let user_analysis_repo = Arc::new(PostgresUserAnalysisRepository::new(db.pool().clone()));
-        let user_repo_arc = Arc::new(UserRepository::new(db.pool().clone()));
-        let drip_repo = Arc::new(DripRepository::new(db.pool().clone()));
-        let discord_repo = Arc::new(PostgresDiscordRepository::new(db.pool().clone()));
-        let platform_identity_repo = Arc::new(PlatformIdentityRepository::new(db.pool().clone()));
-        let cmd_repo = Arc::new(PostgresCommandRepository::new(db.pool().clone()));
-        let cmd_usage_repo = Arc::new(PostgresCommandUsageRepository::new(db.pool().clone()));
-        let redeem_repo = Arc::new(PostgresRedeemRepository::new(db.pool().clone()));
-        let redeem_usage_repo = Arc::new(PostgresRedeemUsageRepository::new(db.pool().clone()));
-        let autostart_repo = Arc::new(PostgresAutostartRepository::new(db.pool().clone()));
-        let obs_repo = Arc::new(PostgresObsRepository::new(db.pool().clone(), encryptor.clone()));
+        maybe_create_owner_user(&repos).await?;
+
+        let creds_repo_arc = repos.credentials.clone();
+        let bot_config_repo = repos.bot_config.clone();
+        let analytics_repo = repos.analytics.clone();
+        let user_analysis_repo = repos.user_analysis.clone();
+        let user_repo_arc = repos.users.clone();
+        let discord_repo = repos.discord.clone();
+        let platform_identity_repo = repos.identities.clone();
+        let cmd_usage_repo = repos.command_usage.clone();
+        let redeem_repo = repos.redeems.clone();
+        let redeem_usage_repo = repos.redeem_usage.clone();
+        let user_notes_repo = repos.user_notes.clone();
 
         // 4) Auth Manager
         let auth_manager = AuthManager::new(
             creds_repo_arc.clone(),
-            platform_config_repo,
+            repos.platform_configs.clone(),
             bot_config_repo.clone(),
         );
         let auth_manager_arc = Arc::new(Mutex::new(auth_manager));
@@ -161,11 +247,24 @@ impl ServerContext {
         // 5) Create EventBus
         let event_bus = Arc::new(EventBus::new());
 
+        // Settings registry: validated bot_config values, hot-reloaded in server.rs
+        let settings = Arc::new(SettingsRegistry::new(bot_config_repo.clone(), event_bus.clone()));
+        settings.load().await?;
+        startup.ready(Subsystem::Repositories, "");
+        let updater = Arc::new(Updater::new(settings.clone(), event_bus.clone(), env!("CARGO_PKG_VERSION"))?);
+        let db_maintenance = Arc::new(DbMaintenance::new(
+            &args.db_path,
+            db.clone(),
+            settings.clone(),
+            event_bus.clone(),
+            data_dir_check,
+        ));
+
         // 6) Construct user manager & services
         let default_user_mgr = DefaultUserManager::new(
             user_repo_arc.clone(),
             platform_identity_repo.clone(),
-            user_analysis_repo.as_ref().clone(),
+            user_analysis_repo.clone(),
         );
         let user_manager_arc = Arc::new(default_user_mgr);
         let user_service = Arc::new(UserService::new(
@@ -173,44 +272,86 @@ impl ServerContext {
             platform_identity_repo.clone(),
         ));
 
-        // Chat cache
+        // Chat cache: one ring per channel, spam/quality trimming in the background
         let trim_policy = TrimPolicy {
             max_age_seconds: Some(24 * 3600),
             spam_score_cutoff: Some(5.0),
             max_total_messages: Some(10_000),
+            max_messages_per_channel: Some(2_000),
             max_messages_per_user: Some(200),
             min_quality_score: Some(0.2),
         };
-        let cache_conf = CacheConfig { trim_policy };
-        let chat_cache = Arc::new(tokio::sync::Mutex::new(
-            ChatCache::new(user_analysis_repo.as_ref().clone(), cache_conf)
-        ));
+        let mut cache_conf = CacheConfig::new(trim_policy);
+        // e.g. {"twitch-irc:mychannel": {"max_messages": 500, "max_age_seconds": 3600}}
+        cache_conf.channel_retention = parse_channel_retention(
+            settings.get("chat_cache.channel_retention").as_deref()
+        );
+        let chat_cache = Arc::new(ChatCache::new(user_analysis_repo.clone(), cache_conf));
+        ChatCache::spawn_trim_task(chat_cache.clone(), std::time::Duration::from_secs(CHAT_CACHE_TRIM_INTERVAL_SECS));
 
-        // Analytics repository for chat logging
-        let analytics_repo = Arc::new(maowbot_core::repositories::postgres::analytics::PostgresAnalyticsRepository::new(
-            db.pool().clone()
-        ));
+        // Re-apply per-channel retention whenever the setting changes
+        {
+            let cache = Arc::downgrade(&chat_cache);
+            settings.watch("chat_cache.channel_retention", move |old, new| {
+                let Some(cache) = cache.upgrade() else { return };
+                let old = parse_channel_retention(old);
+                let new = parse_channel_retention(new);
+                for key in old.keys().filter(|k| !new.contains_key(*k)) {
+                    if let Some((platform, channel)) = key.split_once(':') {
+                        cache.clear_channel_retention(platform, channel);
+                    }
+                }
+                for (key, retention) in &new {
+                    if let Some((platform, channel)) = key.split_once(':') {
+                        cache.set_channel_retention(platform, channel, *retention);
+                    }
+                }
+                info!("Applied chat_cache.channel_retention to {} channel(s)", new.len());
+            });
+        }
+
+        // Warm start: recent chat from the archive, before any platform connects
+        {
+            let hours = settings.get_i64("chat_cache.warm_start_hours").unwrap_or(2);
+            let max_messages = settings.get_i64("chat_cache.warm_start_max_messages").unwrap_or(5_000);
+            match chat_cache.warm_start(repos.chat_archive.as_ref(), user_repo_arc.as_ref(), hours, max_messages).await {
+                Ok(0) => {}
+                Ok(n) => info!("Chat cache warmed with {} archived messages from the last {}h", n, hours),
+                Err(e) => warn!("Chat cache warm start failed: {:?}", e),
+            }
+        }
 
-        // Create a Discord repository
-        let discord_repo = Arc::new(maowbot_core::repositories::postgres::discord::PostgresDiscordRepository::new(db.pool().clone()));
-        
         // Platform manager
         let platform_manager = Arc::new(PlatformManager::new(
             user_service.clone(),
             event_bus.clone(),
             creds_repo_arc.clone(),
             discord_repo.clone(), // Pass Discord repository to PlatformManager
-            encryptor.clone(),
-            db.pool().clone(),
+            repos.obs.clone(),
         ));
+        platform_manager.set_outbound_guard(Arc::new(OutboundGuard::new(
+            settings.clone(),
+            creds_repo_arc.clone(),
+        )));
+        platform_manager.set_outbound_chain(Arc::new(OutboundChain::new(settings.clone())));
+        platform_manager.set_twitch_accounts(Arc::new(TwitchAccountRouter::new(
+            creds_repo_arc.clone(),
+            settings.clone(),
+        )));
+        let chat_identities = Arc::new(ChatIdentityRouter::new(
+            creds_repo_arc.clone(),
+            settings.clone(),
+        ));
+        platform_manager.set_chat_identities(chat_identities.clone());
+        platform_manager.set_ai_shaper(Arc::new(AiResponseShaper::new(settings.clone())));
 
         // Command service - now with platform_manager
+        let workspace_router = Arc::new(WorkspaceRouter::load(repos.clone()).await?);
         let command_service = Arc::new(CommandService::new(
-            cmd_repo.clone(),
+            workspace_router.clone(),
             cmd_usage_repo.clone(),
-            creds_repo_arc.clone(),
             user_service.clone(),
-            bot_config_repo.clone(),
+            settings.clone(),
             platform_manager.clone(),
         ));
 
@@ -232,12 +373,10 @@ impl ServerContext {
 
         // Redeem service
         let redeem_service = Arc::new(RedeemService::new(
-            redeem_repo.clone(),
+            workspace_router.clone(),
             redeem_usage_repo.clone(),
             user_service.clone(),
             platform_manager.clone(),
-            creds_repo_arc.clone(),
-            db.pool().clone(),
             osc_manager_holder.clone(),
             user_repo_arc.clone(),
         ));
@@ -249,10 +388,10 @@ impl ServerContext {
             platform_manager.clone(),
             bot_config_repo.clone(),
             discord_repo.clone(),
+            user_notes_repo.clone(),
         ));
 
         // Event Pipeline Service
-        let event_pipeline_repo = Arc::new(PostgresEventPipelineRepository::new(db.pool().clone()));
         let event_context = Arc::new(maowbot_core::services::event_context::EventContext::new(
             platform_manager.clone(),
             user_service.clone(),
@@ -264,7 +403,7 @@ impl ServerContext {
             )),
             Arc::new(maowbot_core::services::osc_toggle_service::OscToggleService::new(
                 osc_manager_holder.clone(),
-                Arc::new(maowbot_core::repositories::postgres::osc_toggle::PostgresOscToggleRepository::new(db.pool().clone())),
+                repos.osc_toggles.clone(),
             )),
             bot_config_repo.clone(),
             discord_repo.clone(),
@@ -273,36 +412,23 @@ impl ServerContext {
         let event_pipeline_service = Arc::new(EventPipelineService::new(
             event_bus.clone(),
             event_context,
-            event_pipeline_repo,
+            repos.pipeline_system.clone(),
         ).await?);
 
-        // Create the AI repositories
-        info!("🧪 Creating AI repositories...");
-        let ai_provider_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiProviderRepository::new(db.pool().clone()));
-        let ai_credential_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiCredentialRepository::new(db.pool().clone(), encryptor.clone()));
-        let ai_model_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiModelRepository::new(db.pool().clone()));
-        let ai_trigger_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiTriggerRepository::new(db.pool().clone()));
-        let ai_memory_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiMemoryRepository::new(db.pool().clone()));
-        let ai_agent_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiAgentRepository::new(db.pool().clone()));
-        let ai_action_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiActionRepository::new(db.pool().clone()));
-        let ai_prompt_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiSystemPromptRepository::new(db.pool().clone()));
-        let ai_config_repo = Arc::new(maowbot_core::repositories::postgres::ai::PostgresAiConfigurationRepository::new(db.pool().clone(), encryptor.clone()));
-        info!("🧪 AI repositories created successfully");
-
         // Create the AI service with repositories for full database integration
         info!("🧪 Initializing AI service with repositories...");
         let ai_service = match maowbot_ai::plugins::ai_service::AiService::with_repositories(
             user_repo_arc.clone(),
             creds_repo_arc.clone(),
-            ai_provider_repo,
-            ai_credential_repo,
-            ai_model_repo,
-            ai_trigger_repo,
-            ai_memory_repo,
-            ai_agent_repo,
-            ai_action_repo,
-            ai_prompt_repo,
-            ai_config_repo
+            repos.ai_providers.clone(),
+            repos.ai_credentials.clone(),
+            repos.ai_models.clone(),
+            repos.ai_triggers.clone(),
+            repos.ai_memories.clone(),
+            repos.ai_agents.clone(),
+            repos.ai_actions.clone(),
+            repos.ai_prompts.clone(),
+            repos.ai_configurations.clone(),
         ).await {
             Ok(service) => {
                 info!("🧪 AI service initialized successfully with database repositories");
@@ -357,9 +483,10 @@ impl ServerContext {
         let mut plugin_manager = PluginManager::new(
             args.plugin_passphrase.clone(),
             user_repo_arc,
-            drip_repo,
+            repos.drip.clone(),
             discord_repo,
             analytics_repo.clone(),
+            repos.chat_archive.clone(),
             user_analysis_repo,
             platform_identity_repo,
             platform_manager.clone(),
@@ -370,14 +497,15 @@ impl ServerContext {
             redeem_usage_repo.clone(),
             creds_repo_arc.clone(),
             Some(ai_api_impl.clone()),
-            autostart_repo.clone(),
+            repos.autostart.clone(),
         );
         // Let plugin manager see the event bus
         plugin_manager.set_event_bus(event_bus.clone());
         plugin_manager.set_auth_manager(auth_manager_arc.clone());
+        plugin_manager.set_settings_registry(settings.clone());
         
         // Create and set OSC toggle repository
-        let osc_toggle_repo = Arc::new(maowbot_core::repositories::postgres::osc_toggle::PostgresOscToggleRepository::new(db.pool().clone()));
+        let osc_toggle_repo = repos.osc_toggles.clone();
         plugin_manager.set_osc_toggle_repo(osc_toggle_repo.clone());
 
         // subscribe / load etc. (all the same mut calls)
@@ -403,8 +531,8 @@ impl ServerContext {
         // Create the new manager for OSC:
         let mut osc_manager = MaowOscManager::new();
         
-        // Load VRChat destination from database if available for the main manager
-        if let Ok(Some(vrchat_dest)) = bot_config_repo.get_value("osc_vrchat_dest").await {
+        // Load VRChat destination from settings if available for the main manager
+        if let Some(vrchat_dest) = settings.get("osc_vrchat_dest") {
             osc_manager.set_vrchat_dest(Some(vrchat_dest)).await;
         }
 
@@ -427,9 +555,11 @@ impl ServerContext {
         // Update the holder to contain the manager from the Arc
         // We'll create a separate manager for the holder since we can't clone
         let mut holder_manager = MaowOscManager::new();
+        // Both managers report into the same packet counters
+        holder_manager.counters = osc_manager_arc.counters.clone();
         
-        // Load VRChat destination from database if available
-        if let Ok(Some(vrchat_dest)) = bot_config_repo.get_value("osc_vrchat_dest").await {
+        // Load VRChat destination from settings if available
+        if let Some(vrchat_dest) = settings.get("osc_vrchat_dest") {
             holder_manager.set_vrchat_dest(Some(vrchat_dest)).await;
         }
         
@@ -452,6 +582,23 @@ impl ServerContext {
         ));
         plugin_manager.set_osc_toggle_service(osc_toggle_service);
 
+        // Point both OSC managers at a new VRChat destination as soon as it changes
+        {
+            let main_manager = Arc::downgrade(&osc_manager_arc);
+            let holder = osc_manager_holder.clone();
+            settings.watch("osc_vrchat_dest", move |_, new| {
+                let Some(main_manager) = main_manager.upgrade() else { return };
+                let dest = new.map(String::from);
+                let holder = holder.clone();
+                tokio::spawn(async move {
+                    main_manager.set_vrchat_dest(dest.clone()).await;
+                    if let Some(mgr) = holder.read().await.as_ref() {
+                        mgr.set_vrchat_dest(dest).await;
+                    }
+                });
+            });
+        }
+
         plugin_manager.set_osc_manager(osc_manager_arc.clone());
 
         // Create the new robo system:
@@ -459,12 +606,263 @@ impl ServerContext {
 
         plugin_manager.set_osc_manager(Arc::clone(&osc_manager_arc));
 
+        let stream_marker_service = Arc::new(StreamMarkerService::new(
+            repos.stream_markers.clone(),
+            plugin_manager.credentials_repo.clone(),
+            settings.clone(),
+            event_bus.clone(),
+        ));
+        plugin_manager.set_stream_marker_service(stream_marker_service.clone());
+
+        let emote_set_service = Arc::new(EmoteSetService::new(
+            plugin_manager.credentials_repo.clone(),
+            settings.clone(),
+            event_bus.clone(),
+        ));
+
+        let emote_stats_service = Arc::new(EmoteStatsService::new(
+            repos.emote_stats.clone(),
+            plugin_manager.credentials_repo.clone(),
+            event_bus.clone(),
+            settings.clone(),
+            emote_set_service.clone(),
+        ));
+        plugin_manager.set_emote_stats_service(emote_stats_service.clone());
+
+        let clip_service = Arc::new(ClipService::new(
+            repos.clips.clone(),
+            plugin_manager.credentials_repo.clone(),
+            platform_manager.clone(),
+            settings.clone(),
+            event_bus.clone(),
+        ));
+        plugin_manager.set_clip_service(clip_service.clone());
+
+        let localizer = Arc::new(Localizer::new(
+            repos.localization.clone(),
+            settings.clone(),
+        ));
+        if let Err(e) = localizer.load().await {
+            error!("Failed to load language strings, using the bundled packs: {:?}", e);
+        }
+        plugin_manager.set_localizer(localizer.clone());
+
+        let redeem_schedule_service = Arc::new(RedeemScheduleService::new(
+            repos.redeem_schedules.clone(),
+            redeem_repo.clone(),
+            plugin_manager.credentials_repo.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let redeem_approval_service = RedeemApprovalService::new(
+            repos.redeem_approvals.clone(),
+            redeem_service.clone(),
+            plugin_manager.credentials_repo.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        );
+
+        let vrchat_session_service = Arc::new(VRChatSessionService::new(
+            plugin_manager.credentials_repo.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let chat_summarizer = Arc::new(ChatSummarizer::new(
+            ai_service.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let vrchat_presence_service = Arc::new(VRChatPresenceService::new(
+            plugin_manager.credentials_repo.clone(),
+            Some(osc_manager_arc.clone()),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+        plugin_manager.set_vrchat_presence_service(vrchat_presence_service.clone());
+
+        let watchtime_repo = repos.watchtime.clone();
+        let chatter_presence_service = Arc::new(ChatterPresenceService::new(
+            watchtime_repo.clone(),
+            plugin_manager.credentials_repo.clone(),
+            platform_manager.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+        plugin_manager.set_chatter_presence_service(chatter_presence_service.clone());
+
+        let schedule_service = Arc::new(ScheduleService::new(
+            plugin_manager.credentials_repo.clone(),
+            platform_manager.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+        plugin_manager.set_schedule_service(schedule_service.clone());
+
+        let hype_detector = Arc::new(HypeDetector::new(
+            stream_marker_service.clone(),
+            plugin_manager.credentials_repo.clone(),
+            platform_manager.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let responder_service = Arc::new(ResponderService::new(
+            repos.responders.clone(),
+            plugin_manager.credentials_repo.clone(),
+            platform_manager.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let alerting_service = Arc::new(AlertingService::new(
+            repos.alert_rules.clone(),
+            repos.pipeline_logs.clone(),
+            plugin_manager.credentials_repo.clone(),
+            platform_manager.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let osc_chat_relay = Arc::new(OscChatRelayService::new(
+            plugin_manager.credentials_repo.clone(),
+            Some(osc_manager_arc.clone()),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let vrchat_group_service = Arc::new(VRChatGroupService::new(
+            plugin_manager.credentials_repo.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let viewer_card_service = Arc::new(ViewerCardService::new(
+            plugin_manager.user_repo.clone(),
+            plugin_manager.platform_identity_repo.clone(),
+            plugin_manager.user_analysis_repo.clone(),
+            user_notes_repo.clone(),
+            analytics_repo.clone(),
+            redeem_repo.clone(),
+            redeem_usage_repo.clone(),
+            repos.ai_memories.clone(),
+            plugin_manager.credentials_repo.clone(),
+        ));
+
         let plugin_manager_arc = Arc::new(plugin_manager);
 
         // hand to PlatformManager so `get_ai_api()` can succeed
         platform_manager.set_plugin_manager(plugin_manager_arc.clone());
 
+        let donation_service = Arc::new(DonationService::new(
+            repos.donations.clone(),
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let heart_rate_service = Arc::new(HeartRateService::new(
+            event_bus.clone(),
+            settings.clone(),
+            osc_manager_arc.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let midi_service = Arc::new(MidiService::new(
+            repos.midi_mappings.clone(),
+            plugin_manager_arc.credentials_repo.clone(),
+            platform_manager.clone(),
+            osc_manager_arc.clone(),
+            event_bus.clone(),
+            settings.clone(),
+        ));
+
+        let giveaway_service = Arc::new(GiveawayService::new(
+            repos.giveaways.clone(),
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let crowd_vote_service = Arc::new(CrowdVoteService::new(
+            watchtime_repo,
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let protection_service = Arc::new(ProtectionService::new(
+            repos.protection_incidents.clone(),
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let bot_detection_service = Arc::new(BotDetectionService::new(
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+            protection_service.clone(),
+        ));
+
+        let moderation_service = Arc::new(ModerationService::new(
+            repos.moderation_rules.clone(),
+            user_notes_repo.clone(),
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let milestone_service = Arc::new(MilestoneService::new(
+            repos.milestones.clone(),
+            event_bus.clone(),
+            settings.clone(),
+            plugin_manager_arc.clone(),
+        ));
+
+        let stream_session_service = Arc::new(StreamSessionService::new(
+            repos.stream_sessions.clone(),
+            repos.stream_markers.clone(),
+            plugin_manager_arc.credentials_repo.clone(),
+            event_bus.clone(),
+        ));
+
+        let retention = Arc::new(RetentionEngine::new(
+            repos.retention.clone(),
+            repos.chat_archive.clone(),
+            settings.clone(),
+        ));
+
+        let ui_events = Arc::new(UiEventStream::new(
+            repos.ui_journal.clone(),
+            event_bus.clone(),
+            osc_manager_arc.clone(),
+            settings.clone(),
+        ));
+
+        let ui_settings = Arc::new(UiSettingsStore::new(bot_config_repo.clone()));
+
+        let scope_check = Arc::new(ScopeCheckService::new(
+            plugin_manager_arc.credentials_repo.clone(),
+            event_bus.clone(),
+        ));
+
+        let analysis_recompute = Arc::new(AnalysisRecompute::new(
+            repos.analysis_runs.clone(),
+            repos.chat_archive.clone(),
+            plugin_manager_arc.user_analysis_repo.clone(),
+            event_bus.clone(),
+        ));
+        match analysis_recompute.mark_interrupted().await {
+            Ok(0) => {}
+            Ok(n) => info!("{} user analysis run(s) were interrupted; `user analyze --resume` continues them", n),
+            Err(e) => warn!("Could not check for interrupted user analysis runs: {:?}", e),
+        }
+
         Ok(ServerContext {
+            startup,
             db,
             event_bus,
             auth_manager: auth_manager_arc,
@@ -475,19 +873,51 @@ impl ServerContext {
             command_service,
             redeem_service,
             event_pipeline_service,
-            creds_repo: creds_repo_arc,
-            bot_config_repo: bot_config_repo,
-            autostart_repo: autostart_repo as Arc<dyn AutostartRepository + Send + Sync>,
-            command_repo: cmd_repo,
-            command_usage_repo: cmd_usage_repo.clone(),
-            redeem_repo: redeem_repo.clone(),
-            redeem_usage_repo: redeem_usage_repo.clone(),
-            obs_repo,
+            settings,
+            updater,
+            db_maintenance,
+            retention,
+            donation_service,
+            heart_rate_service,
+            midi_service,
+            stream_marker_service,
+            giveaway_service,
+            crowd_vote_service,
+            protection_service,
+            bot_detection_service,
+            moderation_service,
+            emote_stats_service,
+            emote_set_service,
+            ui_events,
+            ui_settings,
+            scope_check,
+            chat_identities,
+            workspace_router,
+            clip_service,
+            localizer,
+            redeem_schedule_service,
+            redeem_approval_service,
+            vrchat_session_service,
+            vrchat_presence_service,
+            chatter_presence_service,
+            schedule_service,
+            hype_detector,
+            milestone_service,
+            stream_session_service,
+            responder_service,
+            alerting_service,
+            osc_chat_relay,
+            vrchat_group_service,
+            viewer_card_service,
+            analysis_recompute,
+            chat_summarizer,
+            secrets: Arc::new(Mutex::new(secrets)),
+            encryptor,
+            repos,
             osc_manager: osc_manager_arc.clone(),
             robo_control,
             oscquery_server: Arc::clone(&osc_manager_arc.oscquery_server),
             ai_service,
-            analytics_repo: analytics_repo as Arc<dyn AnalyticsRepo + Send + Sync>,
             db_logger_control: None, // Will be set in server.rs after spawning
         })
     }
@@ -495,230 +925,170 @@ impl ServerContext {
     /// Shuts down the embedded Postgres instance if used.
     /// (Optional – you can call this after your server loop ends.)
     pub fn stop_postgres(&self) {
-        let pg_bin_dir = "./postgres/bin";
-        let pg_data_dir = "./postgres/data";
-        let _ = crate::portable_postgres::stop_postgres(pg_bin_dir, pg_data_dir);
+        if self.db.postgres().is_some() {
+            let _ = crate::portable_postgres::stop_postgres(PG_BIN_DIR, PG_DATA_DIR);
+        }
     }
 }
 
-/// If `users` table is empty, prompt once for an owner username.
-async fn maybe_create_owner_user(db: &Database) -> Result<(), Error> {
-    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
-        .fetch_one(db.pool())
-        .await?;
-    if count.0 == 0 {
-        println!("No users found in DB. Let's create the owner account now.");
-        println!("Enter the desired owner username:");
-        let mut line = String::new();
-        if std::io::stdin().read_line(&mut line).is_err() {
-            return Err(Error::Io(std::io::Error::new(std::io::ErrorKind::Other, "Failed to read line")));
-        }
-        let owner_username = line.trim().to_string();
-        if owner_username.is_empty() {
-            return Err(Error::Auth("Owner username cannot be empty.".into()));
-        }
+/// The database `ServerContext::new` runs on, and how it got there.
+struct OpenedDb {
+    db: DbConnection,
+    /// Startup findings about the bundled Postgres data dir (empty for other backends)
+    data_dir_check: DataDirCheck,
+    /// Migrations applied while opening it
+    applied: usize,
+}
 
-        let user_id = uuid::Uuid::new_v4();
-        let now = chrono::Utc::now();
-        sqlx::query(
-            r#"
-            INSERT INTO users (user_id, global_username, created_at, last_seen, is_active)
-            VALUES ($1, $2, $3, $4, true)
-            "#
-        )
-            .bind(user_id)
-            .bind(&owner_username)
-            .bind(now)
-            .bind(now)
-            .execute(db.pool())
-            .await?;
-
-        sqlx::query(
-            r#"
-            INSERT INTO bot_config (config_key, config_value)
-            VALUES ('owner_user_id', $1)
-            ON CONFLICT (config_key) DO UPDATE
-                SET config_value = EXCLUDED.config_value
-            "#
-        )
-            .bind(user_id)
-            .execute(db.pool())
-            .await?;
-
-        println!("Owner user '{}' created (user_id={}).", owner_username, user_id);
+/// Starts the bundled Postgres if needed, snapshots it before anything
+/// rewrites the schema, then applies the migrations.
+async fn open_postgres(args: &Args) -> Result<OpenedDb, Error> {
+    let port = 5432;
+
+    if let Err(e) = kill_leftover_postgres_if_any(PG_BIN_DIR, PG_DATA_DIR) {
+        error!("Failed to handle leftover Postgres: {:?}", e);
     }
-    Ok(())
-}
 
-/// Gets a master key from the system keyring or generates a new one.
-/// 
-/// Uses the following strategy:
-/// 1. Try to get/set the key from/to the system keyring (KDE KWallet, GNOME Keyring, or Windows/macOS native)
-/// 2. If that fails, falls back to safely storing the key in a file with secure permissions
-fn get_master_key() -> Result<[u8; 32], Error> {
-    let service_name = "maowbot";
-    let user_name = "master-key";
-
-    // On Linux, log which desktop environment is running to help with debugging
-    #[cfg(target_os = "linux")]
-    {
-        let session_type = std::env::var("XDG_SESSION_TYPE").unwrap_or_default();
-        let desktop_env = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
-        tracing::info!("Detected Linux session: {}, desktop environment: {}", session_type, desktop_env);
+    // Check the cluster while it is stopped; the findings go into every integrity report
+    let data_dir_check = check_data_dir(PG_BIN_DIR, PG_DATA_DIR);
+    for w in &data_dir_check.warnings {
+        warn!("Postgres data dir: {}", w);
+    }
+    for e in &data_dir_check.errors {
+        error!("Postgres data dir: {}", e);
     }
 
-    // Try the OS keyring first
-    let entry_result = Entry::new(service_name, user_name);
-    match entry_result {
-        Ok(entry) => {
-            match entry.get_password() {
-                Ok(base64_key) => {
-                    match decode_key(&base64_key) {
-                        Ok(key) => {
-                            tracing::info!("Retrieved existing master key from system keyring");
-                            return Ok(key);
-                        },
-                        Err(e) => {
-                            tracing::warn!("Found key in keyring but couldn't decode it: {}", e);
-                            // Continue to re-generate key
-                        }
-                    }
-                },
-                Err(e) => {
-                    tracing::info!("Couldn't retrieve key from keyring: {}", e);
-                    // Continue to generate a new key
-                }
-            }
+    ensure_db_initialized(PG_BIN_DIR, PG_DATA_DIR)?;
+    start_postgres(PG_BIN_DIR, PG_DATA_DIR, port)?;
+    create_database(PG_BIN_DIR, port, "maowbot")?;
 
-            // Generate a new key
-            let mut new_key = [0u8; 32];
-            thread_rng().fill(&mut new_key);
-            let base64_key = base64::engine::general_purpose::STANDARD.encode(&new_key);
-            
-            // Try to save it to the keyring
-            match entry.set_password(&base64_key) {
-                Ok(_) => {
-                    tracing::info!("Stored new master key in system keyring");
-                    return Ok(new_key);
-                },
-                Err(e) => {
-                    tracing::warn!("Failed to store key in system keyring: {}. Trying fallback storage...", e);
-                    // Continue to fallback storage
-                }
-            }
-        },
-        Err(e) => {
-            tracing::warn!("Couldn't create keyring entry: {}. Trying fallback storage...", e);
-            // Continue to fallback storage
+    let db_url = &args.db_path;
+    info!("Using Postgres DB URL: {}", db_url);
+    let db = Database::new(db_url).await?;
+
+    // Snapshot before anything rewrites the schema (skipped for a brand-new database)
+    let (pending_migrations, applied_migrations) = db.migration_status().await?;
+    let snapshot_label = if args.nuke_database_and_start_fresh {
+        Some("pre-nuke")
+    } else if !pending_migrations.is_empty() && applied_migrations > 0 {
+        Some("pre-migration")
+    } else {
+        None
+    };
+    if let Some(label) = snapshot_label {
+        info!("Taking a {} snapshot ({} pending migration(s))...", label, pending_migrations.len());
+        if let Err(e) = dump_database(PG_BIN_DIR, db_url, &db_maintenance::snapshot_path(Some(label))) {
+            warn!("Could not take a {} snapshot: {}", label, e);
         }
     }
 
-    // Fallback: Check for a securely stored file
-    // This is a last resort if the OS keyring fails
-    if let Some(key) = try_get_key_from_secure_file()? {
-        return Ok(key);
+    // Check if we should nuke the database and start fresh
+    if args.nuke_database_and_start_fresh {
+        info!("--nuke-database-and-start-fresh flag detected. Dropping all tables...");
+        db.drop_all_tables().await?;
+        info!("Database nuked successfully. Rebuilding from scratch...");
     }
 
-    // If we got here, we need to generate a new key and store it in the fallback
-    let mut new_key = [0u8; 32];
-    thread_rng().fill(&mut new_key);
-    let base64_key = base64::engine::general_purpose::STANDARD.encode(&new_key);
-    
-    // Store in secure file
-    if let Err(e) = store_key_in_secure_file(&base64_key) {
-        tracing::warn!("Failed to store key in secure file: {}", e);
-        tracing::warn!("WARNING: Using a temporary encryption key that will change on restart!");
-        tracing::warn!("To fix this, please set up a compatible keyring service.");
+    // Apply migrations (this will recreate tables if they were dropped)
+    db.migrate().await?;
+
+    Ok(OpenedDb {
+        db: DbConnection::Postgres(db),
+        data_dir_check,
+        applied: pending_migrations.len(),
+    })
+}
+
+/// Opens (or creates) the SQLite file, copying it aside before migrations
+/// change an existing schema, then applies the migrations.
+async fn open_sqlite(args: &Args) -> Result<OpenedDb, Error> {
+    let db = SqliteDatabase::new(&args.db_path).await?;
+
+    let (pending_migrations, applied_migrations) = db.migration_status().await?;
+    let snapshot_label = if args.nuke_database_and_start_fresh {
+        Some("pre-nuke")
+    } else if !pending_migrations.is_empty() && applied_migrations > 0 {
+        Some("pre-migration")
     } else {
-        tracing::info!("Stored new master key in secure file (fallback storage)");
+        None
+    };
+    if let Some(label) = snapshot_label {
+        info!("Taking a {} snapshot ({} pending migration(s))...", label, pending_migrations.len());
+        if let Err(e) = db.copy_to(&db_maintenance::sqlite_snapshot_path(Some(label))).await {
+            warn!("Could not take a {} snapshot: {}", label, e);
+        }
     }
-    
-    Ok(new_key)
-}
 
-/// Decodes a base64 key into a 32-byte array
-fn decode_key(base64_key: &str) -> Result<[u8; 32], Error> {
-    tracing::debug!("Decoding base64 key of length: {}", base64_key.len());
-    
-    let key_bytes = base64::engine::general_purpose::STANDARD.decode(base64_key)
-        .map_err(|e| Error::Parse(format!("Failed to decode key: {:?}", e)))?;
-    
-    let key_len = key_bytes.len();
-    tracing::debug!("Decoded to {} bytes", key_len);
-    
-    // Print first few bytes for debugging (safely)
-    if !key_bytes.is_empty() {
-        let preview = format!("{:02x}{:02x}{:02x}...", 
-            key_bytes[0], 
-            key_bytes.get(1).unwrap_or(&0), 
-            key_bytes.get(2).unwrap_or(&0));
-        tracing::debug!("Key starts with: {}", preview);
+    if args.nuke_database_and_start_fresh {
+        info!("--nuke-database-and-start-fresh flag detected. Dropping all tables...");
+        db.drop_all_tables().await?;
+        info!("Database nuked successfully. Rebuilding from scratch...");
     }
-    
-    key_bytes.try_into()
-        .map_err(|_| Error::Parse(format!("Key was not 32 bytes (got {} bytes)", key_len)))
+
+    db.migrate().await?;
+
+    Ok(OpenedDb {
+        db: DbConnection::Sqlite(db),
+        data_dir_check: DataDirCheck::default(),
+        applied: pending_migrations.len(),
+    })
 }
 
-/// Tries to get the key from a secure file
-fn try_get_key_from_secure_file() -> Result<Option<[u8; 32]>, Error> {
-    let key_file_path = get_secure_key_path()?;
-    
-    if !key_file_path.exists() {
-        return Ok(None);
-    }
-    
-    // Try to read the key file
-    match std::fs::read_to_string(&key_file_path) {
-        Ok(base64_key) => {
-            match decode_key(&base64_key) {
-                Ok(key) => {
-                    tracing::info!("Retrieved master key from secure file: {}", key_file_path.display());
-                    Ok(Some(key))
-                },
-                Err(e) => {
-                    tracing::warn!("Found key file but couldn't decode it: {}", e);
-                    Ok(None)
-                }
-            }
-        },
+/// `--memory-db`: a fresh in-memory database, gone when the server stops.
+async fn open_memory() -> Result<OpenedDb, Error> {
+    let db = memory::open_database().await?;
+    warn!("Running on an in-memory database (--memory-db); nothing will be saved.");
+    Ok(OpenedDb {
+        db: DbConnection::Memory(db),
+        data_dir_check: DataDirCheck::default(),
+        applied: 0,
+    })
+}
+
+/// Parses `chat_cache.channel_retention`, normalizing "platform:channel" keys.
+fn parse_channel_retention(raw: Option<&str>) -> HashMap<String, ChannelRetention> {
+    let Some(raw) = raw else { return HashMap::new() };
+    match serde_json::from_str::<HashMap<String, ChannelRetention>>(raw) {
+        Ok(per_channel) => per_channel.into_iter()
+            .map(|(key, retention)| match key.split_once(':') {
+                Some((platform, channel)) => (channel_key(platform, channel), retention),
+                None => (key, retention),
+            })
+            .collect(),
         Err(e) => {
-            tracing::warn!("Error reading key file: {}", e);
-            Ok(None)
+            warn!("Ignoring invalid chat_cache.channel_retention: {}", e);
+            HashMap::new()
         }
     }
 }
 
-/// Stores the key in a secure file with restrictive permissions
-fn store_key_in_secure_file(base64_key: &str) -> Result<(), Error> {
-    let key_file_path = get_secure_key_path()?;
-    
-    // Ensure parent directory exists
-    if let Some(parent) = key_file_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| Error::Io(e))?;
-    }
-    
-    // Write the key to the file
-    std::fs::write(&key_file_path, base64_key).map_err(|e| Error::Io(e))?;
-    
-    // Set restrictive permissions on Unix-like systems
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(&key_file_path)
-            .map_err(|e| Error::Io(e))?
-            .permissions();
-        // Only owner can read/write
-        perms.set_mode(0o600);
-        std::fs::set_permissions(&key_file_path, perms)
-            .map_err(|e| Error::Io(e))?;
+/// If `users` table is empty, prompt once for an owner username.
+async fn maybe_create_owner_user(repos: &Repositories) -> Result<(), Error> {
+    let (_, user_count) = repos.users.list_page(&UserListQuery { limit: 1, ..Default::default() }).await?;
+    if user_count == 0 {
+        println!("No users found in DB. Let's create the owner account now.");
+        println!("Enter the desired owner username:");
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).is_err() {
+            return Err(Error::Io(std::io::Error::other("Failed to read line")));
+        }
+        let owner_username = line.trim().to_string();
+        if owner_username.is_empty() {
+            return Err(Error::Auth("Owner username cannot be empty.".into()));
+        }
+
+        let now = chrono::Utc::now();
+        let owner = User {
+            user_id: uuid::Uuid::new_v4(),
+            global_username: Some(owner_username.clone()),
+            created_at: now,
+            last_seen: now,
+            is_active: true,
+        };
+        repos.users.create(&owner).await?;
+        repos.bot_config.set_value("owner_user_id", &owner.user_id.to_string()).await?;
+
+        println!("Owner user '{}' created (user_id={}).", owner_username, owner.user_id);
     }
-    
     Ok(())
 }
-
-/// Gets the path to the secure key file
-fn get_secure_key_path() -> Result<std::path::PathBuf, Error> {
-    dirs::config_dir()
-        .map(|dir| dir.join("maowbot").join("master.key"))
-        .ok_or_else(|| Error::Keyring("Could not determine config directory".to_string()))
-}
@@ -0,0 +1,453 @@
+//! maowbot-server/src/control_api.rs
+//!
+//! A small HTTP control surface for Stream Deck and similar button boxes,
+//! which can fire web requests but can't speak gRPC. It is off by default
+//! (`control_api.enabled`) and listens on `control_api.address`. Requests use
+//! the same API tokens as gRPC clients, only as `Authorization: Bearer <token>`
+//! (a query parameter would end up in proxy and access logs); actions need a
+//! moderator or admin token and are written to the audit log. Every action
+//! acts on the whole server, so tokens limited to one workspace can't run them.
+//!
+//!   GET  /api/control/v1/actions                    what can be triggered
+//!   POST /api/control/v1/scene?scene=Live           switch the OBS scene
+//!   POST /api/control/v1/osc-toggle?parameter=Ears  flip an avatar bool
+//!   POST /api/control/v1/alert-test?type=raid       fire a fake alert
+//!   POST /api/control/v1/mute-alerts                toggle `alerts.muted`
+//!
+//! Parameters come from the query string or a JSON object body. Actions
+//! change state, so they only answer POST; link prefetchers and crawlers
+//! that follow a GET can't trigger them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{header::AUTHORIZATION, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use maowbot_common::models::api_token::{ApiToken, AuditLogEntry, Permission};
+use maowbot_common::traits::api::OscApi;
+use maowbot_core::eventbus::BotEvent;
+use maowbot_core::platforms::twitch_eventsub::simulate::simulate_notification;
+use maowbot_core::Error;
+
+use crate::authz::TokenStore;
+use crate::context::ServerContext;
+
+const BASE_PATH: &str = "/api/control/v1";
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7717";
+/// Parameter names that may carry a credential; never passed to an action or audited.
+const CREDENTIAL_KEYS: &[&str] = &["token", "access_token", "api_key", "password", "secret", "authorization"];
+
+/// Something a control surface button can do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlAction {
+    Scene,
+    OscToggle,
+    AlertTest,
+    MuteAlerts,
+}
+
+impl ControlAction {
+    pub const ALL: [ControlAction; 4] = [
+        ControlAction::Scene,
+        ControlAction::OscToggle,
+        ControlAction::AlertTest,
+        ControlAction::MuteAlerts,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ControlAction::Scene => "scene",
+            ControlAction::OscToggle => "osc-toggle",
+            ControlAction::AlertTest => "alert-test",
+            ControlAction::MuteAlerts => "mute-alerts",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<ControlAction> {
+        Self::ALL.into_iter().find(|a| a.as_str().eq_ignore_ascii_case(s))
+    }
+
+    fn params(self) -> &'static str {
+        match self {
+            ControlAction::Scene => "scene=<name> [instance=1]",
+            ControlAction::OscToggle => "parameter=<name> [value=true|false]",
+            ControlAction::AlertTest => "[type=follow] [overrides=<json>]",
+            ControlAction::MuteAlerts => "[muted=true|false]",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            ControlAction::Scene => "Switch the scene of an OBS instance",
+            ControlAction::OscToggle => "Flip a bool avatar parameter, or set it with value",
+            ControlAction::AlertTest => "Fire a simulated Twitch alert (follow, sub, raid, cheer, ...)",
+            ControlAction::MuteAlerts => "Toggle alerts.muted, or set it with muted",
+        }
+    }
+
+    /// Whether the action reaches past any one workspace (OBS, the avatar,
+    /// server-wide settings), so a workspace-scoped token can't run it.
+    fn is_global(self) -> bool {
+        match self {
+            ControlAction::Scene
+            | ControlAction::OscToggle
+            | ControlAction::AlertTest
+            | ControlAction::MuteAlerts => true,
+        }
+    }
+}
+
+/// Why `token` may not run `action`, if it's limited to a workspace and the action is global.
+fn workspace_denial(token: &ApiToken, action: ControlAction) -> Option<String> {
+    let workspace_id = token.workspace_id?;
+    action.is_global().then(|| format!(
+        "limited to workspace {}; {} acts on the whole server", workspace_id, action.as_str()
+    ))
+}
+
+/// The workspace a token is limited to, as the audit log records it.
+fn audit_workspace(token: &ApiToken) -> Option<String> {
+    token.workspace_id.map(|id| id.to_string())
+}
+
+fn is_credential_key(key: &str) -> bool {
+    CREDENTIAL_KEYS.iter().any(|k| k.eq_ignore_ascii_case(key))
+}
+
+/// The query string with a JSON object body merged over it. Credential keys
+/// such as `token` are left out, so they never reach the audit log.
+pub fn parse_params(query: HashMap<String, String>, body: &[u8]) -> Result<Map<String, Value>, String> {
+    let mut params: Map<String, Value> = query.into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect();
+    if body.iter().any(|b| !b.is_ascii_whitespace()) {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Object(fields)) => params.extend(fields),
+            Ok(_) => return Err("The request body must be a JSON object".to_string()),
+            Err(e) => return Err(format!("Invalid JSON body: {}", e)),
+        }
+    }
+    params.retain(|key, _| !is_credential_key(key));
+    Ok(params)
+}
+
+fn param_str(params: &Map<String, Value>, key: &str) -> Option<String> {
+    match params.get(key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// A bool given as JSON or as true/false, on/off, yes/no or 1/0.
+pub fn param_bool(params: &Map<String, Value>, key: &str) -> Result<Option<bool>, Error> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Bool(b)) => Ok(Some(*b)),
+        Some(other) => {
+            let text = other.as_str().map(str::to_string).unwrap_or_else(|| other.to_string());
+            match text.trim().to_ascii_lowercase().as_str() {
+                "true" | "on" | "yes" | "1" => Ok(Some(true)),
+                "false" | "off" | "no" | "0" => Ok(Some(false)),
+                "" => Ok(None),
+                _ => Err(Error::ValidationError(format!("'{}' must be true or false, not '{}'", key, text))),
+            }
+        }
+    }
+}
+
+fn reply(status: StatusCode, body: Value) -> Response {
+    (status, Json(body)).into_response()
+}
+
+fn failure(status: StatusCode, message: impl Into<String>) -> Response {
+    reply(status, json!({ "ok": false, "error": message.into() }))
+}
+
+fn error_status(e: &Error) -> StatusCode {
+    match e {
+        Error::NotFound(_) => StatusCode::NOT_FOUND,
+        Error::ValidationError(_) | Error::Parse(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::BAD_GATEWAY,
+    }
+}
+
+pub struct ControlApi {
+    ctx: Arc<ServerContext>,
+    tokens: TokenStore,
+    /// Last value sent per avatar parameter, so a toggle knows what to flip to
+    osc_values: Mutex<HashMap<String, bool>>,
+}
+
+impl ControlApi {
+    /// The token on the request if it grants `required`; otherwise the error
+    /// response, with the attempt written to the audit log.
+    async fn authenticate(
+        &self,
+        headers: &HeaderMap,
+        required: Permission,
+        path: &str,
+    ) -> Result<ApiToken, Response> {
+        let secret = headers.get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(|v| v.trim().to_string());
+        let token = match &secret {
+            Some(secret) => self.tokens.authenticate(secret).await,
+            None => None,
+        };
+        let Some(token) = token else {
+            let reason = if secret.is_some() { "invalid or revoked API token" } else { "missing API token" };
+            self.audit_denied(None, path, reason.to_string());
+            return Err(failure(StatusCode::UNAUTHORIZED, reason));
+        };
+        if !token.role.grants(required) {
+            let reason = format!("requires {} permission", required);
+            self.audit_denied(Some(&token), path, reason.clone());
+            return Err(failure(StatusCode::FORBIDDEN, format!("Token '{}' ({}) {}", token.name, token.role, reason)));
+        }
+        Ok(token)
+    }
+
+    fn audit_denied(&self, token: Option<&ApiToken>, path: &str, reason: String) {
+        self.tokens.audit(AuditLogEntry {
+            audit_id: 0,
+            token_id: token.map(|t| t.token_id),
+            client_name: token.map(|t| t.name.clone()),
+            role: token.map(|t| t.role.to_string()),
+            method: path.to_string(),
+            workspace: token.and_then(audit_workspace),
+            allowed: false,
+            reason: Some(reason),
+            created_at: Utc::now(),
+            target: None,
+            before_value: None,
+            after_value: None,
+            outcome: None,
+        });
+    }
+
+    async fn perform(&self, action: ControlAction, params: &Map<String, Value>) -> Result<String, Error> {
+        match action {
+            ControlAction::Scene => {
+                let scene = param_str(params, "scene")
+                    .ok_or_else(|| Error::ValidationError("Missing 'scene'".to_string()))?;
+                let instance = match param_str(params, "instance") {
+                    Some(n) => n.parse::<u32>()
+                        .map_err(|_| Error::ValidationError(format!("Invalid OBS instance '{}'", n)))?,
+                    None => 1,
+                };
+                let obs = self.ctx.platform_manager.get_obs_instance(instance).await?;
+                obs.get_client().set_current_scene(&scene).await
+                    .map_err(|e| Error::Platform(format!("OBS {} could not switch to '{}': {}", instance, scene, e)))?;
+                Ok(format!("Switched OBS {} to scene '{}'", instance, scene))
+            }
+            ControlAction::OscToggle => {
+                let parameter = param_str(params, "parameter")
+                    .ok_or_else(|| Error::ValidationError("Missing 'parameter'".to_string()))?;
+                let value = match param_bool(params, "value")? {
+                    Some(value) => value,
+                    None => !self.osc_values.lock().unwrap().get(&parameter).copied().unwrap_or(false),
+                };
+                self.ctx.plugin_manager.osc_send_avatar_parameter_bool(&parameter, value).await?;
+                self.osc_values.lock().unwrap().insert(parameter.clone(), value);
+                Ok(format!("Set {} to {}", parameter, value))
+            }
+            ControlAction::AlertTest => {
+                let kind = param_str(params, "type").unwrap_or_else(|| "follow".to_string());
+                let overrides = match params.get("overrides") {
+                    Some(Value::String(raw)) if !raw.trim().is_empty() => Some(
+                        serde_json::from_str::<Value>(raw)
+                            .map_err(|e| Error::ValidationError(format!("Invalid overrides JSON: {}", e)))?,
+                    ),
+                    Some(value @ Value::Object(_)) => Some(value.clone()),
+                    _ => None,
+                };
+                let simulated = simulate_notification(&kind, overrides.as_ref())?;
+                self.ctx.event_bus.publish(BotEvent::TwitchEventSub(simulated.event)).await;
+                Ok(format!("Fired a test {} alert", simulated.sub_type))
+            }
+            ControlAction::MuteAlerts => {
+                let muted = match param_bool(params, "muted")? {
+                    Some(muted) => muted,
+                    None => !self.ctx.settings.get_bool("alerts.muted").unwrap_or(false),
+                };
+                self.ctx.settings.set("alerts.muted", if muted { "true" } else { "false" }).await?;
+                Ok(if muted { "Alerts muted".to_string() } else { "Alerts unmuted".to_string() })
+            }
+        }
+    }
+}
+
+async fn list_actions(
+    State(api): State<Arc<ControlApi>>,
+    headers: HeaderMap,
+) -> Response {
+    let path = format!("{}/actions", BASE_PATH);
+    if let Err(response) = api.authenticate(&headers, Permission::Read, &path).await {
+        return response;
+    }
+    let actions: Vec<Value> = ControlAction::ALL.iter()
+        .map(|a| json!({
+            "name": a.as_str(),
+            "path": format!("{}/{}", BASE_PATH, a.as_str()),
+            "params": a.params(),
+            "description": a.description(),
+        }))
+        .collect();
+    reply(StatusCode::OK, json!({
+        "ok": true,
+        "actions": actions,
+        "alerts_muted": api.ctx.settings.get_bool("alerts.muted").unwrap_or(false),
+    }))
+}
+
+async fn run_action(
+    State(api): State<Arc<ControlApi>>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> Response {
+    let Some(action) = ControlAction::parse(&name) else {
+        return failure(StatusCode::NOT_FOUND, format!("Unknown action '{}'", name));
+    };
+    let path = format!("{}/{}", BASE_PATH, action.as_str());
+    let token = match api.authenticate(&headers, Permission::Moderate, &path).await {
+        Ok(token) => token,
+        Err(response) => return response,
+    };
+    if let Some(reason) = workspace_denial(&token, action) {
+        api.audit_denied(Some(&token), &path, reason.clone());
+        return failure(StatusCode::FORBIDDEN, format!("Token '{}' is {}", token.name, reason));
+    }
+    let params = match parse_params(query, &body) {
+        Ok(params) => params,
+        Err(message) => return failure(StatusCode::BAD_REQUEST, message),
+    };
+
+    let result = api.perform(action, &params).await;
+    api.tokens.audit(AuditLogEntry {
+        audit_id: 0,
+        token_id: Some(token.token_id),
+        client_name: Some(token.name.clone()),
+        role: Some(token.role.to_string()),
+        method: path,
+        workspace: audit_workspace(&token),
+        allowed: true,
+        reason: None,
+        created_at: Utc::now(),
+        target: Some(format!("control:{}", action.as_str())),
+        before_value: None,
+        after_value: Some(Value::Object(params)),
+        outcome: Some(match &result {
+            Ok(_) => "Ok".to_string(),
+            Err(e) => format!("{:?}", error_status(e)),
+        }),
+    });
+    match result {
+        Ok(message) => reply(StatusCode::OK, json!({ "ok": true, "message": message })),
+        Err(e) => {
+            info!("Control API {} by '{}' failed: {}", action.as_str(), token.name, e);
+            failure(error_status(&e), e.to_string())
+        }
+    }
+}
+
+/// Starts the control API when `control_api.enabled` is on; it stops with the server.
+pub fn spawn(ctx: Arc<ServerContext>, tokens: TokenStore) -> Option<JoinHandle<()>> {
+    if !ctx.settings.get_bool("control_api.enabled").unwrap_or(false) {
+        return None;
+    }
+    let address = ctx.settings.get("control_api.address")
+        .filter(|a| !a.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let mut shutdown_rx = ctx.event_bus.shutdown_rx.clone();
+    let api = Arc::new(ControlApi { ctx, tokens, osc_values: Mutex::new(HashMap::new()) });
+    let app = Router::new()
+        .route(&format!("{}/actions", BASE_PATH), get(list_actions))
+        .route(&format!("{}/{{action}}", BASE_PATH), post(run_action))
+        .with_state(api);
+
+    Some(tokio::spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(address.trim()).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Control API could not listen on {}: {}", address, e);
+                return;
+            }
+        };
+        info!("Control API listening on http://{}{}", address.trim(), BASE_PATH);
+        let shutdown = async move {
+            while shutdown_rx.changed().await.is_ok() {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+        };
+        if let Err(e) = axum::serve(listener, app).with_graceful_shutdown(shutdown).await {
+            error!("Control API error: {}", e);
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_merge_query_and_body() {
+        let query = HashMap::from([
+            ("scene".to_string(), "Starting".to_string()),
+            ("token".to_string(), "mwb_secret".to_string()),
+            ("value".to_string(), "off".to_string()),
+        ]);
+        let params = parse_params(
+            query.clone(),
+            br#"{"scene": "Live", "instance": 2, "Token": "mwb_body", "password": "hunter2"}"#,
+        ).unwrap();
+        assert_eq!(param_str(&params, "scene").as_deref(), Some("Live"));
+        assert_eq!(param_str(&params, "instance").as_deref(), Some("2"));
+        assert!(!params.contains_key("token") && !params.contains_key("Token"));
+        assert!(!params.contains_key("password"));
+        assert_eq!(param_bool(&params, "value").unwrap(), Some(false));
+        assert_eq!(param_bool(&params, "muted").unwrap(), None);
+
+        assert_eq!(parse_params(query.clone(), b"  ").unwrap().len(), 2);
+        assert!(parse_params(query, b"[1, 2]").is_err());
+        assert_eq!(ControlAction::parse("OSC-Toggle"), Some(ControlAction::OscToggle));
+    }
+
+    #[test]
+    fn test_workspace_tokens_cannot_run_global_actions() {
+        use maowbot_common::models::api_token::ApiRole;
+        use uuid::Uuid;
+
+        let mut token = ApiToken {
+            token_id: Uuid::new_v4(),
+            name: "deck".to_string(),
+            role: ApiRole::Moderator,
+            token_hash: String::new(),
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+            workspace_id: None,
+        };
+        assert!(ControlAction::ALL.iter().all(|a| workspace_denial(&token, *a).is_none()));
+        assert_eq!(audit_workspace(&token), None);
+
+        let brand = Uuid::new_v4();
+        token.workspace_id = Some(brand);
+        assert!(ControlAction::ALL.iter().all(|a| workspace_denial(&token, *a).is_some()));
+        assert_eq!(audit_workspace(&token), Some(brand.to_string()));
+    }
+}
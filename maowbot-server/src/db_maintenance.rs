@@ -0,0 +1,471 @@
+//! maowbot-server/src/db_maintenance.rs
+//!
+//! Keeps the database healthy on top of `portable_postgres`: scheduled pg_dump
+//! snapshots (plus one before migrations), checkpoint + VACUUM compaction with
+//! server.log rotation, and integrity checks run at startup and on demand.
+//! On SQLite the snapshots are `VACUUM INTO` copies and the integrity check
+//! is `PRAGMA integrity_check`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use maowbot_core::db::DbConnection;
+use maowbot_core::eventbus::{BotEvent, EventBus};
+use maowbot_core::settings::SettingsRegistry;
+use maowbot_core::Error;
+
+use crate::portable_postgres::{self, DataDirCheck, PG_BIN_DIR, PG_DATA_DIR, SNAPSHOT_DIR};
+
+/// How often the schedule wakes up to see whether a snapshot or compaction is due.
+const SCHEDULE_TICK: Duration = Duration::from_secs(15 * 60);
+/// server.log is rotated during compaction once it passes this size.
+const MAX_SERVER_LOG_BYTES: u64 = 50 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IntegrityReport {
+    pub checked_at: DateTime<Utc>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl IntegrityReport {
+    pub fn ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompactionReport {
+    pub database_bytes_before: i64,
+    pub database_bytes_after: i64,
+    pub wal_bytes_before: u64,
+    pub wal_bytes_after: u64,
+    pub log_rotated: bool,
+    pub duration: Duration,
+    pub warnings: Vec<String>,
+}
+
+/// Extensions of the snapshot files: pg_dump archives and SQLite copies.
+const SNAPSHOT_EXTENSIONS: [&str; 2] = ["dump", "sqlite"];
+
+/// Builds a snapshot file path: `maowbot-20261016-093000[-label].dump`.
+pub fn snapshot_path(label: Option<&str>) -> PathBuf {
+    snapshot_path_with_extension(label, "dump")
+}
+
+/// The SQLite counterpart of `snapshot_path`: `maowbot-20261016-093000[-label].sqlite`.
+pub fn sqlite_snapshot_path(label: Option<&str>) -> PathBuf {
+    snapshot_path_with_extension(label, "sqlite")
+}
+
+fn snapshot_path_with_extension(label: Option<&str>, extension: &str) -> PathBuf {
+    let mut name = format!("maowbot-{}", Utc::now().format("%Y%m%d-%H%M%S"));
+    if let Some(label) = label.map(sanitize_label).filter(|l| !l.is_empty()) {
+        name.push('-');
+        name.push_str(&label);
+    }
+    Path::new(SNAPSHOT_DIR).join(format!("{}.{}", name, extension))
+}
+
+fn sanitize_label(label: &str) -> String {
+    label.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .take(32)
+        .collect()
+}
+
+pub struct DbMaintenance {
+    db_url: String,
+    db: DbConnection,
+    settings: Arc<SettingsRegistry>,
+    event_bus: Arc<EventBus>,
+    /// Startup data-dir findings, folded into every integrity report
+    data_dir_check: DataDirCheck,
+    last_integrity: Mutex<Option<IntegrityReport>>,
+    last_compacted: Mutex<Option<DateTime<Utc>>>,
+    /// One snapshot/compaction at a time
+    busy: tokio::sync::Mutex<()>,
+}
+
+impl DbMaintenance {
+    pub fn new(
+        db_url: &str,
+        db: DbConnection,
+        settings: Arc<SettingsRegistry>,
+        event_bus: Arc<EventBus>,
+        data_dir_check: DataDirCheck,
+    ) -> Self {
+        Self {
+            db_url: db_url.to_string(),
+            db,
+            settings,
+            event_bus,
+            data_dir_check,
+            last_integrity: Mutex::new(None),
+            last_compacted: Mutex::new(None),
+            busy: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// True when the server is running the bundled cluster rather than an external Postgres.
+    pub fn embedded() -> bool {
+        Path::new(PG_DATA_DIR).join("PG_VERSION").exists()
+    }
+
+    pub fn last_integrity(&self) -> Option<IntegrityReport> {
+        self.last_integrity.lock().unwrap().clone()
+    }
+
+    pub fn last_compacted(&self) -> Option<DateTime<Utc>> {
+        *self.last_compacted.lock().unwrap()
+    }
+
+    pub async fn database_size(&self) -> Result<i64, Error> {
+        match &self.db {
+            DbConnection::Postgres(db) => Ok(sqlx::query_scalar("SELECT pg_database_size(current_database())")
+                .fetch_one(db.pool())
+                .await?),
+            DbConnection::Sqlite(db) | DbConnection::Memory(db) => Ok(sqlx::query_scalar(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()"
+            )
+                .fetch_one(db.pool())
+                .await?),
+        }
+    }
+
+    pub async fn server_version(&self) -> Result<String, Error> {
+        match &self.db {
+            DbConnection::Postgres(db) => Ok(sqlx::query_scalar("SHOW server_version")
+                .fetch_one(db.pool())
+                .await?),
+            DbConnection::Sqlite(db) => {
+                let version: String = sqlx::query_scalar("SELECT sqlite_version()")
+                    .fetch_one(db.pool())
+                    .await?;
+                Ok(format!("SQLite {}", version))
+            }
+            DbConnection::Memory(db) => {
+                let version: String = sqlx::query_scalar("SELECT sqlite_version()")
+                    .fetch_one(db.pool())
+                    .await?;
+                Ok(format!("SQLite {} (in memory)", version))
+            }
+        }
+    }
+
+    /// Dumps the database (SQLite: copies it) to postgres/snapshots, then prunes down to `db.snapshot_keep`.
+    pub async fn create_snapshot(&self, label: Option<&str>) -> Result<SnapshotInfo, Error> {
+        let _busy = self.busy.lock().await;
+        let path = match &self.db {
+            DbConnection::Postgres(_) => {
+                let path = snapshot_path(label);
+                let db_url = self.db_url.clone();
+                let dest = path.clone();
+                tokio::task::spawn_blocking(move || portable_postgres::dump_database(PG_BIN_DIR, &db_url, &dest))
+                    .await
+                    .map_err(|e| Error::Internal(format!("Snapshot task failed: {}", e)))??;
+                path
+            }
+            DbConnection::Sqlite(db) => {
+                let path = sqlite_snapshot_path(label);
+                db.copy_to(&path).await?;
+                path
+            }
+            DbConnection::Memory(_) => {
+                return Err(Error::ValidationError(
+                    "The in-memory database (--memory-db) has no snapshots".into()
+                ));
+            }
+        };
+
+        let keep = self.settings.get_u64("db.snapshot_keep").unwrap_or(7) as usize;
+        let removed = Self::prune_snapshots(keep)?;
+        if removed > 0 {
+            info!("Removed {} old database snapshot(s)", removed);
+        }
+
+        let size_bytes = std::fs::metadata(&path)?.len();
+        Ok(SnapshotInfo {
+            name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            size_bytes,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Snapshots in postgres/snapshots, newest first.
+    pub fn list_snapshots() -> Result<Vec<SnapshotInfo>, Error> {
+        let entries = match std::fs::read_dir(SNAPSHOT_DIR) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut snapshots: Vec<SnapshotInfo> = entries
+            .flatten()
+            .filter(|e| e.path().extension()
+                .map(|x| SNAPSHOT_EXTENSIONS.iter().any(|ext| x == *ext))
+                .unwrap_or(false))
+            .filter_map(|e| {
+                let meta = e.metadata().ok()?;
+                Some(SnapshotInfo {
+                    name: e.file_name().to_string_lossy().to_string(),
+                    size_bytes: meta.len(),
+                    created_at: meta.modified().ok().map(DateTime::<Utc>::from)?,
+                })
+            })
+            .collect();
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        Ok(snapshots)
+    }
+
+    fn prune_snapshots(keep: usize) -> Result<usize, Error> {
+        let snapshots = Self::list_snapshots()?;
+        let mut removed = 0;
+        for old in snapshots.iter().skip(keep.max(1)) {
+            match std::fs::remove_file(Path::new(SNAPSHOT_DIR).join(&old.name)) {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Could not remove old snapshot {}: {}", old.name, e),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// CHECKPOINT (lets Postgres recycle WAL segments), VACUUM ANALYZE (or
+    /// VACUUM FULL, which locks tables while it rewrites them) and a
+    /// server.log rotation. On SQLite: a WAL checkpoint, VACUUM (always a
+    /// full rewrite) and ANALYZE.
+    pub async fn compact(&self, full: bool) -> Result<CompactionReport, Error> {
+        let _busy = self.busy.lock().await;
+        let started = Instant::now();
+        let mut warnings = Vec::new();
+
+        let database_bytes_before = self.database_size().await?;
+        let wal_bytes_before = portable_postgres::wal_size(PG_DATA_DIR);
+
+        let mut log_rotated = false;
+        match &self.db {
+            DbConnection::Postgres(db) => {
+                // CHECKPOINT needs superuser (or pg_checkpoint); an external server may refuse it
+                if let Err(e) = sqlx::query("CHECKPOINT").execute(db.pool()).await {
+                    warnings.push(format!("CHECKPOINT skipped: {}", e));
+                }
+                let vacuum = if full { "VACUUM (FULL, ANALYZE)" } else { "VACUUM (ANALYZE)" };
+                sqlx::query(vacuum).execute(db.pool()).await?;
+
+                log_rotated = match portable_postgres::rotate_server_log(PG_DATA_DIR, MAX_SERVER_LOG_BYTES) {
+                    Ok(rotated) => rotated,
+                    Err(e) => {
+                        warnings.push(format!("server.log rotation failed: {}", e));
+                        false
+                    }
+                };
+            }
+            DbConnection::Memory(db) => {
+                sqlx::query("VACUUM").execute(db.pool()).await?;
+            }
+            DbConnection::Sqlite(db) => {
+                if let Err(e) = sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(db.pool()).await {
+                    warnings.push(format!("WAL checkpoint skipped: {}", e));
+                }
+                sqlx::query("VACUUM").execute(db.pool()).await?;
+                sqlx::query("ANALYZE").execute(db.pool()).await?;
+            }
+        }
+
+        let report = CompactionReport {
+            database_bytes_before,
+            database_bytes_after: self.database_size().await?,
+            wal_bytes_before,
+            wal_bytes_after: portable_postgres::wal_size(PG_DATA_DIR),
+            log_rotated,
+            duration: started.elapsed(),
+            warnings,
+        };
+        *self.last_compacted.lock().unwrap() = Some(Utc::now());
+        info!(
+            "Database compacted in {:?}: {} -> {} bytes, WAL {} -> {} bytes",
+            report.duration, report.database_bytes_before, report.database_bytes_after,
+            report.wal_bytes_before, report.wal_bytes_after
+        );
+        Ok(report)
+    }
+
+    /// Checks checksum failures, transaction-ID wraparound, half-applied
+    /// migrations and invalid indexes, plus the startup data-dir findings
+    /// (SQLite: `PRAGMA integrity_check`, foreign keys and migrations).
+    pub async fn check_integrity(&self) -> IntegrityReport {
+        let mut errors = self.data_dir_check.errors.clone();
+        let mut warnings = self.data_dir_check.warnings.clone();
+
+        if let Err(e) = self.db.ping().await {
+            errors.push(format!("Database is not answering queries: {}", e));
+        }
+        match &self.db {
+            DbConnection::Postgres(db) => Self::check_postgres(db.pool(), &mut errors, &mut warnings).await,
+            DbConnection::Sqlite(db) => Self::check_sqlite(db.pool(), &mut errors, &mut warnings).await,
+            // Its foreign keys are off (users live outside it), and it started empty
+            DbConnection::Memory(_) => {}
+        }
+
+        let report = IntegrityReport { checked_at: Utc::now(), errors, warnings };
+        *self.last_integrity.lock().unwrap() = Some(report.clone());
+        report
+    }
+
+    async fn check_postgres(pool: &sqlx::PgPool, errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+        // Only counted when the cluster was initialized with data checksums (PG 12+)
+        let checksum_failures: Result<Option<i64>, _> = sqlx::query_scalar(
+            "SELECT checksum_failures FROM pg_stat_database WHERE datname = current_database()"
+        ).fetch_one(pool).await;
+        if let Ok(Some(n)) = checksum_failures {
+            if n > 0 {
+                errors.push(format!("{} data page checksum failure(s); restore from a snapshot", n));
+            }
+        }
+
+        let xid_age: Result<i32, _> = sqlx::query_scalar(
+            "SELECT age(datfrozenxid) FROM pg_database WHERE datname = current_database()"
+        ).fetch_one(pool).await;
+        match xid_age {
+            Ok(age) if age > 1_500_000_000 => errors.push(format!(
+                "Transaction ID age is {}; run `system db compact` before Postgres forces a shutdown", age
+            )),
+            Ok(age) if age > 1_000_000_000 => warnings.push(format!(
+                "Transaction ID age is {}; a VACUUM is due", age
+            )),
+            Ok(_) => {}
+            Err(e) => warnings.push(format!("Could not read transaction ID age: {}", e)),
+        }
+
+        let failed_migrations: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT version, description FROM _sqlx_migrations WHERE NOT success ORDER BY version"
+        ).fetch_all(pool).await.unwrap_or_default();
+        for (version, description) in failed_migrations {
+            errors.push(format!("Migration {} ({}) did not finish; restore the pre-migration snapshot", version, description));
+        }
+
+        let invalid_indexes: Vec<String> = sqlx::query_scalar(
+            "SELECT c.relname::text FROM pg_index i JOIN pg_class c ON c.oid = i.indexrelid WHERE NOT i.indisvalid"
+        ).fetch_all(pool).await.unwrap_or_default();
+        for index in invalid_indexes {
+            warnings.push(format!("Index {} is invalid; REINDEX it", index));
+        }
+
+    }
+
+    async fn check_sqlite(pool: &sqlx::SqlitePool, errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+        match sqlx::query_scalar::<_, String>("PRAGMA integrity_check").fetch_all(pool).await {
+            Ok(problems) => errors.extend(
+                problems.into_iter().filter(|p| p != "ok").map(|p| format!("Integrity check: {}", p))
+            ),
+            Err(e) => warnings.push(format!("Could not run the integrity check: {}", e)),
+        }
+
+        let violations: Vec<(String,)> = sqlx::query_as("SELECT \"table\" FROM pragma_foreign_key_check")
+            .fetch_all(pool).await.unwrap_or_default();
+        if !violations.is_empty() {
+            warnings.push(format!("{} row(s) point at missing parents; restore from a snapshot", violations.len()));
+        }
+
+        let failed_migrations: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT version, description FROM _sqlx_migrations WHERE NOT success ORDER BY version"
+        ).fetch_all(pool).await.unwrap_or_default();
+        for (version, description) in failed_migrations {
+            errors.push(format!("Migration {} ({}) did not finish; restore the pre-migration snapshot", version, description));
+        }
+    }
+
+    /// Runs the integrity check once and announces any errors.
+    pub async fn check_integrity_on_startup(&self) {
+        let report = self.check_integrity().await;
+        for w in &report.warnings {
+            warn!("Database integrity: {}", w);
+        }
+        for e in &report.errors {
+            error!("Database integrity: {}", e);
+        }
+        if !report.ok() {
+            self.event_bus.publish(BotEvent::SystemMessage(format!(
+                "Database integrity check found {} problem(s); see `system db status`.",
+                report.errors.len()
+            ))).await;
+        }
+    }
+
+    /// Takes snapshots every `db.snapshot_interval_hours` and compacts every
+    /// `db.compact_interval_hours`; either is disabled by setting it to 0.
+    pub fn spawn_schedule(self: &Arc<Self>) -> JoinHandle<()> {
+        let weak = Arc::downgrade(self);
+        let mut shutdown_rx = self.event_bus.shutdown_rx.clone();
+        let started = Utc::now();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(SCHEDULE_TICK);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await; // first tick fires immediately
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        let Some(maint) = weak.upgrade() else { break };
+                        maint.run_due(started).await;
+                    }
+                    Ok(_) = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            info!("Database maintenance schedule: shutting down cleanly.");
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    async fn run_due(&self, started: DateTime<Utc>) {
+        let now = Utc::now();
+        let due = |hours: u64, last: Option<DateTime<Utc>>| {
+            hours > 0 && now - last.unwrap_or(started) >= chrono::Duration::hours(hours as i64)
+        };
+
+        let snapshot_hours = match self.db {
+            DbConnection::Memory(_) => 0,
+            _ => self.settings.get_u64("db.snapshot_interval_hours").unwrap_or(24),
+        };
+        let last_snapshot = Self::list_snapshots().ok()
+            .and_then(|s| s.first().map(|s| s.created_at));
+        if due(snapshot_hours, last_snapshot) {
+            if let Err(e) = self.create_snapshot(Some("scheduled")).await {
+                error!("Scheduled database snapshot failed: {:?}", e);
+            }
+        }
+
+        let compact_hours = self.settings.get_u64("db.compact_interval_hours").unwrap_or(168);
+        if due(compact_hours, self.last_compacted()) {
+            if let Err(e) = self.compact(false).await {
+                error!("Scheduled database compaction failed: {:?}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_path_sanitizes_label() {
+        let path = snapshot_path(Some("pre migration/../x"));
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("maowbot-"));
+        assert!(name.ends_with("-pre-migration----x.dump"));
+        assert_eq!(path.parent().unwrap(), Path::new(SNAPSHOT_DIR));
+    }
+}
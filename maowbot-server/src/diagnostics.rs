@@ -0,0 +1,375 @@
+//! maowbot-server/src/diagnostics.rs
+//!
+//! On-demand dependency checks behind `RunDiagnostics`: database round trip,
+//! every stored credential, the Twitch account routing, the EventSub
+//! subscriptions, OSC, the VRChat sessions, the OBS connections and free disk
+//! space. Each check stands
+//! alone and reports ok / warning / failed / skipped with a short reason, so
+//! one unreachable service never hides the others.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use tracing::debug;
+
+use maowbot_common::models::platform::{Platform, PlatformCredential};
+use maowbot_core::disk::free_disk_bytes;
+use maowbot_core::platforms::twitch::client::TwitchHelixClient;
+use maowbot_core::platforms::twitch::routing::{AccountRoute, TwitchOperation};
+use maowbot_core::platforms::vrchat::client::SessionState;
+use maowbot_core::services::twitch::broadcaster_helix;
+use maowbot_core::services::twitch::scope_check::ScopeReport;
+
+use crate::context::ServerContext;
+
+/// Checks that can be asked for by name.
+pub const CATEGORIES: &[&str] = &["database", "credentials", "routing", "eventsub", "osc", "vrchat", "obs", "disk"];
+
+/// How long any single remote check may take.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+/// A database round trip slower than this is worth a warning.
+const SLOW_DATABASE_MS: u64 = 250;
+/// Free space below these levels warns, then fails.
+const DISK_WARN_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+const DISK_FAIL_BYTES: u64 = 1024 * 1024 * 1024;
+/// Tokens expiring sooner than this without a refresh token are flagged.
+const EXPIRY_WARN: chrono::Duration = chrono::Duration::hours(24);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Ok,
+    Skipped,
+    Warning,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub category: &'static str,
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub latency: Option<Duration>,
+}
+
+impl CheckResult {
+    fn new(category: &'static str, name: impl Into<String>, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self { category, name: name.into(), status, message: message.into(), latency: None }
+    }
+
+    fn timed(mut self, started: Instant) -> Self {
+        self.latency = Some(started.elapsed());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticsReport {
+    pub started_at: DateTime<Utc>,
+    pub duration: Duration,
+    pub checks: Vec<CheckResult>,
+}
+
+impl DiagnosticsReport {
+    /// The worst status of any check; skipped checks don't count.
+    pub fn overall(&self) -> CheckStatus {
+        self.checks.iter()
+            .map(|c| c.status)
+            .filter(|s| *s != CheckStatus::Skipped)
+            .max()
+            .unwrap_or(CheckStatus::Ok)
+    }
+}
+
+/// Runs the checks in `categories` (all of them when empty) and collects the results.
+pub async fn run_diagnostics(ctx: &Arc<ServerContext>, categories: &[String]) -> DiagnosticsReport {
+    let wanted = |category: &str| categories.is_empty() || categories.iter().any(|c| c.eq_ignore_ascii_case(category));
+    let started_at = Utc::now();
+    let started = Instant::now();
+
+    let mut checks = Vec::new();
+    if wanted("database") {
+        checks.push(check_database(ctx).await);
+    }
+    if wanted("credentials") {
+        checks.extend(check_credentials(ctx).await);
+    }
+    if wanted("routing") {
+        checks.extend(check_account_routing(ctx).await);
+    }
+    if wanted("eventsub") {
+        checks.push(check_eventsub(ctx).await);
+    }
+    if wanted("osc") {
+        checks.push(check_osc(ctx).await);
+    }
+    if wanted("vrchat") {
+        checks.extend(check_vrchat(ctx).await);
+    }
+    if wanted("obs") {
+        checks.extend(check_obs(ctx).await);
+    }
+    if wanted("disk") {
+        checks.push(check_disk().await);
+    }
+
+    DiagnosticsReport { started_at, duration: started.elapsed(), checks }
+}
+
+async fn check_database(ctx: &ServerContext) -> CheckResult {
+    let started = Instant::now();
+    let backend = ctx.db.backend_name();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, ctx.db.ping()).await;
+    let elapsed = started.elapsed().as_millis() as u64;
+    let check = match result {
+        Ok(Ok(_)) if elapsed > SLOW_DATABASE_MS => {
+            CheckResult::new("database", backend, CheckStatus::Warning, format!("Answered, but slowly ({} ms)", elapsed))
+        }
+        Ok(Ok(_)) => CheckResult::new("database", backend, CheckStatus::Ok, format!("Answered in {} ms", elapsed)),
+        Ok(Err(e)) => CheckResult::new("database", backend, CheckStatus::Failed, format!("Query failed: {}", e)),
+        Err(_) => CheckResult::new("database", backend, CheckStatus::Failed, "No answer within 10 s"),
+    };
+    check.timed(started)
+}
+
+/// Token expiry for every credential, plus a live validation for Twitch tokens.
+/// VRChat sessions are covered by the `vrchat` check.
+async fn check_credentials(ctx: &ServerContext) -> Vec<CheckResult> {
+    let platforms = [Platform::Twitch, Platform::TwitchIRC, Platform::TwitchEventSub, Platform::Discord, Platform::Kick];
+    let mut checks = Vec::new();
+    for platform in platforms {
+        let creds = match ctx.repos.credentials.list_credentials_for_platform(&platform).await {
+            Ok(creds) => creds,
+            Err(e) => {
+                checks.push(CheckResult::new("credentials", platform.to_string(), CheckStatus::Failed, format!("Could not list credentials: {}", e)));
+                continue;
+            }
+        };
+        for cred in creds {
+            checks.push(check_credential(&cred).await);
+        }
+    }
+    if checks.is_empty() {
+        checks.push(CheckResult::new("credentials", "all", CheckStatus::Skipped, "No credentials stored"));
+    }
+    checks
+}
+
+async fn check_credential(cred: &PlatformCredential) -> CheckResult {
+    let name = format!("{} {}", cred.platform, cred.user_name);
+    let now = Utc::now();
+    if let Some(expires_at) = cred.expires_at {
+        if cred.refresh_token.is_none() {
+            if expires_at < now {
+                return CheckResult::new("credentials", name, CheckStatus::Failed, "Expired and has no refresh token; log in again");
+            }
+            if expires_at < now + EXPIRY_WARN {
+                return CheckResult::new("credentials", name, CheckStatus::Warning, format!(
+                    "Expires {} with no refresh token", expires_at.format("%Y-%m-%d %H:%M UTC")
+                ));
+            }
+        }
+    }
+
+    if !matches!(cred.platform, Platform::Twitch | Platform::TwitchIRC | Platform::TwitchEventSub) {
+        return CheckResult::new("credentials", name, CheckStatus::Ok, "Stored and not expired (not verified remotely)");
+    }
+    let started = Instant::now();
+    let token = cred.primary_token.trim_start_matches("oauth:");
+    let client = TwitchHelixClient::new(token, "");
+    let check = match tokio::time::timeout(CHECK_TIMEOUT, client.validate_token()).await {
+        Ok(Ok(Some(valid))) => match ScopeReport::new(cred, &valid.scopes, true, None) {
+            report if !report.missing.is_empty() => CheckResult::new("credentials", name, CheckStatus::Warning, format!(
+                "Valid for {} but missing {} ({} degraded); run `{}`",
+                valid.login, report.missing.join(", "), report.affected.join(", "),
+                report.reauth_command().unwrap_or_default()
+            )),
+            _ => CheckResult::new("credentials", name, CheckStatus::Ok, format!(
+                "Valid for {} ({} min left)", valid.login, valid.expires_in / 60
+            )),
+        },
+        // The refresh task renews these; only a refused refresh needs a new login
+        Ok(Ok(None)) if cred.refresh_token.is_some() => {
+            CheckResult::new("credentials", name, CheckStatus::Warning, "Twitch rejected the token; waiting for a refresh")
+        }
+        Ok(Ok(None)) => CheckResult::new("credentials", name, CheckStatus::Failed, "Twitch rejected the token; log in again"),
+        Ok(Err(e)) => CheckResult::new("credentials", name, CheckStatus::Warning, format!("Could not reach Twitch: {}", e)),
+        Err(_) => CheckResult::new("credentials", name, CheckStatus::Warning, "Twitch did not answer within 10 s"),
+    };
+    check.timed(started)
+}
+
+/// Which account each Twitch operation would run as under `twitch.account_routing`.
+async fn check_account_routing(ctx: &ServerContext) -> Vec<CheckResult> {
+    let accounts = match ctx.platform_manager.twitch_accounts() {
+        Ok(accounts) => accounts,
+        Err(e) => return vec![CheckResult::new("routing", "twitch", CheckStatus::Skipped, e.to_string())],
+    };
+    let mut checks = Vec::new();
+    for op in TwitchOperation::ALL {
+        let check = match accounts.account_for(op).await {
+            Ok(routed) if routed.is_bot || routed.route == AccountRoute::Broadcaster => {
+                CheckResult::new("routing", op.as_str(), CheckStatus::Ok, format!(
+                    "Runs as {} ({})", routed.credential.user_name, routed.route.as_str()
+                ))
+            }
+            Ok(routed) => CheckResult::new("routing", op.as_str(), CheckStatus::Ok, format!(
+                "Runs as the broadcaster {}; no bot account is set up", routed.credential.user_name
+            )),
+            // Nothing else can stand in for a bot_only route
+            Err(e) if accounts.policy().route(op) == AccountRoute::BotOnly => {
+                CheckResult::new("routing", op.as_str(), CheckStatus::Failed, e.to_string())
+            }
+            Err(e) => CheckResult::new("routing", op.as_str(), CheckStatus::Warning, e.to_string()),
+        };
+        checks.push(check);
+    }
+    checks
+}
+
+/// The broadcaster's EventSub subscriptions should all be enabled while connected.
+async fn check_eventsub(ctx: &ServerContext) -> CheckResult {
+    let helix = match broadcaster_helix(&*ctx.repos.credentials).await {
+        Ok(helix) => helix,
+        Err(e) => return CheckResult::new("eventsub", "subscriptions", CheckStatus::Skipped, e.to_string()),
+    };
+    let started = Instant::now();
+    let subscriptions = match tokio::time::timeout(CHECK_TIMEOUT, helix.client.list_eventsub_subscriptions()).await {
+        Ok(Ok(listed)) => listed.subscriptions,
+        Ok(Err(e)) => {
+            return CheckResult::new("eventsub", "subscriptions", CheckStatus::Failed, format!("Could not list subscriptions: {}", e)).timed(started);
+        }
+        Err(_) => return CheckResult::new("eventsub", "subscriptions", CheckStatus::Warning, "Twitch did not answer within 10 s").timed(started),
+    };
+
+    let broken: Vec<String> = subscriptions.iter()
+        .filter(|s| s.status != "enabled")
+        .map(|s| format!("{} ({})", s.sub_type, s.status))
+        .collect();
+    let enabled = subscriptions.len() - broken.len();
+    let check = if subscriptions.is_empty() {
+        CheckResult::new("eventsub", "subscriptions", CheckStatus::Warning, "No subscriptions; is the EventSub runtime running?")
+    } else if enabled == 0 {
+        CheckResult::new("eventsub", "subscriptions", CheckStatus::Failed, format!("None enabled: {}", broken.join(", ")))
+    } else if !broken.is_empty() {
+        CheckResult::new("eventsub", "subscriptions", CheckStatus::Warning, format!(
+            "{} enabled, {} not: {}", enabled, broken.len(), broken.join(", ")
+        ))
+    } else {
+        CheckResult::new("eventsub", "subscriptions", CheckStatus::Ok, format!("{} enabled", enabled))
+    };
+    check.timed(started)
+}
+
+async fn check_osc(ctx: &ServerContext) -> CheckResult {
+    match ctx.osc_manager.get_status().await {
+        Ok(status) if !status.is_running => CheckResult::new("osc", "server", CheckStatus::Warning, "OSC is not running"),
+        Ok(status) if !status.vrchat_connected => CheckResult::new("osc", "server", CheckStatus::Warning, format!(
+            "Listening on {}, but VRChat hasn't been seen",
+            status.listening_port.map(|p| p.to_string()).unwrap_or_else(|| "?".into())
+        )),
+        Ok(status) => CheckResult::new("osc", "server", CheckStatus::Ok, format!(
+            "Listening on {}, VRChat connected",
+            status.listening_port.map(|p| p.to_string()).unwrap_or_else(|| "?".into())
+        )),
+        Err(e) => CheckResult::new("osc", "server", CheckStatus::Failed, format!("Could not read OSC status: {}", e)),
+    }
+}
+
+async fn check_vrchat(ctx: &ServerContext) -> Vec<CheckResult> {
+    let started = Instant::now();
+    let statuses = match tokio::time::timeout(CHECK_TIMEOUT, ctx.vrchat_session_service.check_all()).await {
+        Ok(Ok(statuses)) => statuses,
+        Ok(Err(e)) => return vec![CheckResult::new("vrchat", "sessions", CheckStatus::Failed, e.to_string())],
+        Err(_) => return vec![CheckResult::new("vrchat", "sessions", CheckStatus::Warning, "VRChat did not answer within 10 s")],
+    };
+    if statuses.is_empty() {
+        return vec![CheckResult::new("vrchat", "sessions", CheckStatus::Skipped, "No VRChat accounts")];
+    }
+    statuses.into_iter()
+        .map(|s| {
+            let (status, message) = match (s.state, s.error) {
+                (Some(SessionState::Valid), _) => (CheckStatus::Ok, format!(
+                    "Session valid{}", s.display_name.map(|n| format!(" ({})", n)).unwrap_or_default()
+                )),
+                (Some(SessionState::TwoFactorRequired), _) => (CheckStatus::Failed, "Two-factor login was never finished".to_string()),
+                (Some(SessionState::Expired), _) => (CheckStatus::Failed, "Session expired; log in again".to_string()),
+                (None, error) => (CheckStatus::Warning, format!(
+                    "VRChat unreachable: {}", error.unwrap_or_else(|| "unknown error".into())
+                )),
+            };
+            CheckResult::new("vrchat", s.user_name, status, message).timed(started)
+        })
+        .collect()
+}
+
+async fn check_obs(ctx: &ServerContext) -> Vec<CheckResult> {
+    let instances = match ctx.repos.obs.list_instances().await {
+        Ok(instances) => instances,
+        Err(e) => return vec![CheckResult::new("obs", "instances", CheckStatus::Failed, format!("Could not list OBS instances: {}", e))],
+    };
+    if instances.is_empty() {
+        return vec![CheckResult::new("obs", "instances", CheckStatus::Skipped, "No OBS instances configured")];
+    }
+    let mut checks = Vec::new();
+    for instance in instances {
+        let name = format!("obs-{} ({}:{})", instance.instance_number, instance.host, instance.port);
+        let started = Instant::now();
+        let check = match ctx.platform_manager.get_obs_instance(instance.instance_number).await {
+            Ok(runtime) => {
+                let client = runtime.get_client();
+                if client.is_connected().await {
+                    let version = tokio::time::timeout(CHECK_TIMEOUT, client.get_version()).await;
+                    match version {
+                        Ok(Ok(version)) => CheckResult::new("obs", name, CheckStatus::Ok, format!("Connected, OBS {}", version)),
+                        _ => CheckResult::new("obs", name, CheckStatus::Warning, "Connected, but OBS didn't answer a version request"),
+                    }
+                } else {
+                    CheckResult::new("obs", name, CheckStatus::Warning, "Runtime started but not connected")
+                }
+            }
+            Err(e) => {
+                debug!("diagnostics: no OBS runtime for {}: {}", instance.instance_number, e);
+                CheckResult::new("obs", name, CheckStatus::Warning, "Not connected; start it with `obs connect`")
+            }
+        };
+        checks.push(check.timed(started));
+    }
+    checks
+}
+
+/// Free space on the volume holding the working directory (and the bundled database).
+async fn check_disk() -> CheckResult {
+    let free = match free_disk_bytes(Path::new(".")).await {
+        Ok(free) => free,
+        Err(e) => return CheckResult::new("disk", "free space", CheckStatus::Skipped, e),
+    };
+    let text = format!("{:.1} GiB free", free as f64 / (1024.0 * 1024.0 * 1024.0));
+    let status = if free < DISK_FAIL_BYTES {
+        CheckStatus::Failed
+    } else if free < DISK_WARN_BYTES {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Ok
+    };
+    CheckResult::new("disk", "free space", status, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overall_is_the_worst_non_skipped_status() {
+        let report = |statuses: &[CheckStatus]| DiagnosticsReport {
+            started_at: Utc::now(),
+            duration: Duration::ZERO,
+            checks: statuses.iter().map(|s| CheckResult::new("test", "x", *s, "")).collect(),
+        };
+        assert_eq!(report(&[CheckStatus::Ok, CheckStatus::Skipped]).overall(), CheckStatus::Ok);
+        assert_eq!(report(&[CheckStatus::Warning, CheckStatus::Ok]).overall(), CheckStatus::Warning);
+        assert_eq!(report(&[CheckStatus::Failed, CheckStatus::Warning]).overall(), CheckStatus::Failed);
+        assert_eq!(report(&[]).overall(), CheckStatus::Ok);
+    }
+}
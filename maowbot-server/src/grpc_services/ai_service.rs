@@ -9,14 +9,12 @@ use chrono::Utc;
 use tracing::{info, error, debug};
 use prost_types;
 use serde_json;
-use maowbot_ai::plugins::ai_service::AiService as AiServicePlugin;
-use std::any::Any;
 
 // Helper function to convert protobuf Struct to serde_json::Value
 fn struct_to_json_value(s: &prost_types::Struct) -> serde_json::Value {
     let mut map = serde_json::Map::new();
     for (k, v) in &s.fields {
-        map.insert(k.clone(), value_to_json(&v));
+        map.insert(k.clone(), value_to_json(v));
     }
     serde_json::Value::Object(map)
 }
@@ -0,0 +1,181 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    alerting_service_server::AlertingService,
+    AlertRule as ProtoAlertRule, AlertRuleResponse, AlertDelivery, RaisedAlert as ProtoRaisedAlert,
+    ListAlertRulesRequest, ListAlertRulesResponse,
+    AddAlertRuleRequest, UpdateAlertRuleRequest,
+    DeleteAlertRuleRequest, DeleteAlertRuleResponse,
+    ListRecentAlertsRequest, ListRecentAlertsResponse,
+    TestAlertRequest,
+};
+use maowbot_common::models::alert_rule::{AlertKind, AlertRoute, AlertRule, AlertSeverity, QuietHours};
+use maowbot_core::services::alerting::{
+    AlertRuleEdit, AlertingService as Alerting, DeliveryOutcome, NewAlertRule, RaisedAlert,
+};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::authz::{audit_value, AuditNote, Caller};
+use super::convert::{to_timestamp, to_status};
+
+const DEFAULT_RECENT: usize = 20;
+
+pub struct AlertingServiceImpl {
+    alerting: Arc<Alerting>,
+}
+
+impl AlertingServiceImpl {
+    pub fn new(alerting: Arc<Alerting>) -> Self {
+        Self { alerting }
+    }
+}
+
+fn caller_name<T>(request: &Request<T>) -> String {
+    request.extensions().get::<Caller>()
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "console".to_string())
+}
+
+fn rule_to_proto(r: AlertRule) -> ProtoAlertRule {
+    ProtoAlertRule {
+        rule_id: r.rule_id.to_string(),
+        name: r.name,
+        kinds: r.kinds.iter().map(|k| k.to_string()).collect(),
+        min_severity: r.min_severity.to_string(),
+        route: r.route.to_string(),
+        target: r.target.unwrap_or_default(),
+        dedupe_secs: r.dedupe_secs,
+        quiet_hours: r.quiet_hours.map(|q| q.to_string()).unwrap_or_default(),
+        enabled: r.enabled,
+    }
+}
+
+fn raised_to_proto(raised: RaisedAlert) -> ProtoRaisedAlert {
+    let alert = raised.alert;
+    ProtoRaisedAlert {
+        kind: alert.kind.to_string(),
+        severity: alert.severity.to_string(),
+        key: alert.key,
+        title: alert.title,
+        message: alert.message,
+        raised_at: Some(to_timestamp(alert.raised_at)),
+        deliveries: raised.deliveries.into_iter().map(|(rule, outcome)| {
+            let (outcome, error) = match outcome {
+                DeliveryOutcome::Sent => ("sent", String::new()),
+                DeliveryOutcome::Deduped => ("deduped", String::new()),
+                DeliveryOutcome::Quiet => ("quiet", String::new()),
+                DeliveryOutcome::Failed(e) => ("failed", e),
+            };
+            AlertDelivery { rule, outcome: outcome.to_string(), error }
+        }).collect(),
+    }
+}
+
+fn parse_kinds(kinds: &[String]) -> Result<Vec<AlertKind>, Status> {
+    kinds.iter().map(|k| k.parse::<AlertKind>()).collect::<Result<_, _>>().map_err(to_status)
+}
+
+/// None for an empty string.
+fn parse_quiet_hours(text: &str) -> Result<Option<QuietHours>, Status> {
+    if text.trim().is_empty() {
+        Ok(None)
+    } else {
+        text.parse().map(Some).map_err(to_status)
+    }
+}
+
+fn rule_response(rule: AlertRule) -> Response<AlertRuleResponse> {
+    Response::new(AlertRuleResponse { rule: Some(rule_to_proto(rule)) })
+}
+
+#[tonic::async_trait]
+impl AlertingService for AlertingServiceImpl {
+    async fn list_alert_rules(&self, _request: Request<ListAlertRulesRequest>) -> Result<Response<ListAlertRulesResponse>, Status> {
+        let rules = self.alerting.list_rules().await.map_err(to_status)?;
+        Ok(Response::new(ListAlertRulesResponse {
+            rules: rules.into_iter().map(rule_to_proto).collect(),
+        }))
+    }
+
+    async fn add_alert_rule(&self, request: Request<AddAlertRuleRequest>) -> Result<Response<AlertRuleResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let min_severity = if req.min_severity.is_empty() {
+            AlertSeverity::Warning
+        } else {
+            req.min_severity.parse().map_err(to_status)?
+        };
+        let route: AlertRoute = req.route.parse().map_err(to_status)?;
+        let rule = self.alerting.add_rule(NewAlertRule {
+            name: req.name,
+            kinds: parse_kinds(&req.kinds)?,
+            min_severity,
+            route,
+            target: Some(req.target),
+            dedupe_secs: req.dedupe_secs,
+            quiet_hours: parse_quiet_hours(&req.quiet_hours)?,
+        }).await.map_err(to_status)?;
+        info!("Alert rule '{}' added by '{}'", rule.name, caller);
+        audit.change(format!("alert_rule:{}", rule.name), None, audit_value(&rule));
+        Ok(rule_response(rule))
+    }
+
+    async fn update_alert_rule(&self, request: Request<UpdateAlertRuleRequest>) -> Result<Response<AlertRuleResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let before = self.alerting.get_rule(&req.name).await.ok();
+        let rule = self.alerting.edit_rule(&req.name, AlertRuleEdit {
+            kinds: req.kinds.map(|k| parse_kinds(&k.kinds)).transpose()?,
+            min_severity: req.min_severity.map(|s| s.parse::<AlertSeverity>()).transpose().map_err(to_status)?,
+            route: req.route.map(|r| r.parse::<AlertRoute>()).transpose().map_err(to_status)?,
+            target: req.target,
+            dedupe_secs: req.dedupe_secs,
+            quiet_hours: req.quiet_hours.as_deref().map(parse_quiet_hours).transpose()?,
+            enabled: req.enabled,
+        }).await.map_err(to_status)?;
+        info!("Alert rule '{}' updated by '{}'", rule.name, caller);
+        audit.change(format!("alert_rule:{}", rule.name), before.as_ref().and_then(audit_value), audit_value(&rule));
+        Ok(rule_response(rule))
+    }
+
+    async fn delete_alert_rule(&self, request: Request<DeleteAlertRuleRequest>) -> Result<Response<DeleteAlertRuleResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let name = request.into_inner().name;
+        let before = self.alerting.get_rule(&name).await.ok();
+        self.alerting.delete_rule(&name).await.map_err(to_status)?;
+        info!("Alert rule '{}' deleted by '{}'", name, caller);
+        audit.change(format!("alert_rule:{}", name), before.as_ref().and_then(audit_value), None);
+        Ok(Response::new(DeleteAlertRuleResponse {}))
+    }
+
+    async fn list_recent_alerts(&self, request: Request<ListRecentAlertsRequest>) -> Result<Response<ListRecentAlertsResponse>, Status> {
+        let limit = match request.into_inner().limit {
+            0 => DEFAULT_RECENT,
+            n => n as usize,
+        };
+        Ok(Response::new(ListRecentAlertsResponse {
+            alerts: self.alerting.recent_alerts(limit).into_iter().map(raised_to_proto).collect(),
+        }))
+    }
+
+    async fn test_alert(&self, request: Request<TestAlertRequest>) -> Result<Response<ProtoRaisedAlert>, Status> {
+        let caller = caller_name(&request);
+        let req = request.into_inner();
+        let kind = if req.kind.is_empty() {
+            AlertKind::PlatformDisconnect
+        } else {
+            req.kind.parse().map_err(to_status)?
+        };
+        let severity = if req.severity.is_empty() {
+            AlertSeverity::Warning
+        } else {
+            req.severity.parse().map_err(to_status)?
+        };
+        info!("Test {} alert raised by '{}'", kind, caller);
+        let raised = self.alerting.test_alert(kind, severity).await;
+        Ok(Response::new(raised_to_proto(raised)))
+    }
+}
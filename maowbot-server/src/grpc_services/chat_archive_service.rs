@@ -0,0 +1,238 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    chat_archive_service_server::ChatArchiveService,
+    ArchivedChatMessage, RetentionPolicy,
+    SearchMessagesRequest, SearchMessagesResponse,
+    GetLastSeenRequest, GetLastSeenResponse,
+    ListRetentionPoliciesRequest, ListRetentionPoliciesResponse,
+    SetRetentionPolicyRequest, DeleteRetentionPolicyRequest,
+    ChatActivityBucket as ProtoActivityBucket, GetChatActivityRequest, GetChatActivityResponse,
+};
+use maowbot_common::models::analytics::{
+    peak_buckets, ChatActivityBucket, ChatMessage, ChatSearchQuery, CHAT_ACTIVITY_BUCKET_SECONDS,
+};
+use maowbot_common::traits::repository_traits::{ChatArchiveRepository, UserRepo};
+use maowbot_core::settings::SettingsRegistry;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, error};
+use uuid::Uuid;
+use super::convert::{to_timestamp, from_timestamp};
+
+pub struct ChatArchiveServiceImpl {
+    archive_repo: Arc<dyn ChatArchiveRepository>,
+    user_repo: Arc<dyn UserRepo + Send + Sync>,
+    settings: Arc<SettingsRegistry>,
+}
+
+impl ChatArchiveServiceImpl {
+    pub fn new(
+        archive_repo: Arc<dyn ChatArchiveRepository>,
+        user_repo: Arc<dyn UserRepo + Send + Sync>,
+        settings: Arc<SettingsRegistry>,
+    ) -> Self {
+        Self { archive_repo, user_repo, settings }
+    }
+
+    /// Resolves a user UUID or global username.
+    async fn resolve_user(&self, user: &str) -> Result<Uuid, Status> {
+        if let Ok(id) = Uuid::parse_str(user) {
+            return Ok(id);
+        }
+        self.user_repo.get_by_global_username(user).await
+            .map_err(|e| Status::internal(format!("Failed to look up user: {}", e)))?
+            .map(|u| u.user_id)
+            .ok_or_else(|| Status::not_found(format!("User '{}' not found", user)))
+    }
+
+    /// Converts messages, looking up each author's username once.
+    async fn messages_to_proto(&self, messages: Vec<ChatMessage>) -> Vec<ArchivedChatMessage> {
+        let mut usernames: HashMap<Uuid, String> = HashMap::new();
+        let mut out = Vec::with_capacity(messages.len());
+        for msg in messages {
+            if let std::collections::hash_map::Entry::Vacant(e) = usernames.entry(msg.user_id) {
+                let name = match self.user_repo.get(msg.user_id).await {
+                    Ok(Some(u)) => u.global_username.unwrap_or_default(),
+                    _ => String::new(),
+                };
+                e.insert(name);
+            }
+            out.push(ArchivedChatMessage {
+                message_id: msg.message_id.to_string(),
+                platform: msg.platform,
+                channel: msg.channel,
+                user_id: msg.user_id.to_string(),
+                username: usernames[&msg.user_id].clone(),
+                text: msg.message_text,
+                timestamp: Some(to_timestamp(msg.timestamp)),
+            });
+        }
+        out
+    }
+}
+
+/// Longest range one activity query may cover.
+const MAX_ACTIVITY_RANGE_DAYS: i64 = 90;
+
+fn activity_to_proto(b: ChatActivityBucket) -> ProtoActivityBucket {
+    ProtoActivityBucket {
+        platform: b.platform,
+        channel: b.channel,
+        bucket_start: Some(to_timestamp(b.bucket_start)),
+        message_count: b.message_count,
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    let s = s.trim().to_string();
+    if s.is_empty() { None } else { Some(s) }
+}
+
+#[tonic::async_trait]
+impl ChatArchiveService for ChatArchiveServiceImpl {
+    async fn search_messages(
+        &self,
+        request: Request<SearchMessagesRequest>,
+    ) -> Result<Response<SearchMessagesResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = match non_empty(req.user) {
+            Some(user) => Some(self.resolve_user(&user).await?),
+            None => None,
+        };
+
+        let query = ChatSearchQuery {
+            user_id,
+            platform: non_empty(req.platform),
+            channel: non_empty(req.channel),
+            text: non_empty(req.text),
+            since: req.since.as_ref().and_then(from_timestamp),
+            until: req.until.as_ref().and_then(from_timestamp),
+            limit: if req.limit > 0 { req.limit.min(500) as i64 } else { 50 },
+            offset: req.offset.max(0) as i64,
+        };
+
+        let (messages, total_count) = self.archive_repo.search_messages(&query).await
+            .map_err(|e| {
+                error!("Chat search failed: {:?}", e);
+                Status::internal(format!("Failed to search chat messages: {}", e))
+            })?;
+
+        Ok(Response::new(SearchMessagesResponse {
+            messages: self.messages_to_proto(messages).await,
+            total_count,
+        }))
+    }
+
+    async fn get_last_seen(
+        &self,
+        request: Request<GetLastSeenRequest>,
+    ) -> Result<Response<GetLastSeenResponse>, Status> {
+        let req = request.into_inner();
+        let user = non_empty(req.user)
+            .ok_or_else(|| Status::invalid_argument("user is required"))?;
+        let user_id = self.resolve_user(&user).await?;
+        let platform = non_empty(req.platform);
+        let channel = non_empty(req.channel);
+
+        let msg = self.archive_repo
+            .last_message_for_user(user_id, platform.as_deref(), channel.as_deref())
+            .await
+            .map_err(|e| Status::internal(format!("Failed to look up last message: {}", e)))?;
+
+        let message = match msg {
+            Some(m) => self.messages_to_proto(vec![m]).await.pop(),
+            None => None,
+        };
+        Ok(Response::new(GetLastSeenResponse {
+            found: message.is_some(),
+            message,
+        }))
+    }
+
+    async fn list_retention_policies(
+        &self,
+        _: Request<ListRetentionPoliciesRequest>,
+    ) -> Result<Response<ListRetentionPoliciesResponse>, Status> {
+        let policies = self.archive_repo.list_retention_policies().await
+            .map_err(|e| Status::internal(format!("Failed to list retention policies: {}", e)))?;
+        // Defaults to 30, the same fallback maintenance uses
+        let default_retention_days = self.settings
+            .get_i64("chat_logging.default_retention_days")
+            .unwrap_or(30) as i32;
+
+        Ok(Response::new(ListRetentionPoliciesResponse {
+            policies: policies.into_iter().map(|p| RetentionPolicy {
+                platform: p.platform,
+                channel: p.channel,
+                retention_days: p.retention_days,
+                updated_at: Some(to_timestamp(p.updated_at)),
+            }).collect(),
+            default_retention_days,
+        }))
+    }
+
+    async fn set_retention_policy(
+        &self,
+        request: Request<SetRetentionPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        let (Some(platform), Some(channel)) = (non_empty(req.platform), non_empty(req.channel)) else {
+            return Err(Status::invalid_argument("platform and channel are required"));
+        };
+        self.archive_repo.set_retention_policy(&platform, &channel, req.retention_days).await
+            .map_err(|e| match e {
+                maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+                other => Status::internal(format!("Failed to set retention policy: {}", other)),
+            })?;
+        info!("Chat retention for {}/{} set to {} days", platform, channel, req.retention_days);
+        Ok(Response::new(()))
+    }
+
+    async fn delete_retention_policy(
+        &self,
+        request: Request<DeleteRetentionPolicyRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        let deleted = self.archive_repo.delete_retention_policy(req.platform.trim(), req.channel.trim()).await
+            .map_err(|e| Status::internal(format!("Failed to delete retention policy: {}", e)))?;
+        if !deleted {
+            return Err(Status::not_found(format!(
+                "No retention policy for {}/{}", req.platform, req.channel
+            )));
+        }
+        info!("Chat retention policy for {}/{} removed", req.platform, req.channel);
+        Ok(Response::new(()))
+    }
+
+    async fn get_chat_activity(
+        &self,
+        request: Request<GetChatActivityRequest>,
+    ) -> Result<Response<GetChatActivityResponse>, Status> {
+        let req = request.into_inner();
+        let until = req.until.as_ref().and_then(from_timestamp).unwrap_or_else(Utc::now);
+        let since = req.since.as_ref().and_then(from_timestamp).unwrap_or(until - Duration::hours(24));
+        if since >= until {
+            return Err(Status::invalid_argument("since must be before until"));
+        }
+        if until - since > Duration::days(MAX_ACTIVITY_RANGE_DAYS) {
+            return Err(Status::invalid_argument(format!(
+                "Activity ranges are limited to {} days", MAX_ACTIVITY_RANGE_DAYS
+            )));
+        }
+        let platform = non_empty(req.platform);
+        let channel = non_empty(req.channel);
+
+        let buckets = self.archive_repo
+            .chat_activity(platform.as_deref(), channel.as_deref(), since, until)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to load chat activity: {}", e)))?;
+        let peaks = peak_buckets(&buckets, req.peak_count.clamp(0, 100) as usize);
+
+        Ok(Response::new(GetChatActivityResponse {
+            buckets: buckets.into_iter().map(activity_to_proto).collect(),
+            peaks: peaks.into_iter().map(activity_to_proto).collect(),
+            bucket_seconds: CHAT_ACTIVITY_BUCKET_SECONDS as i32,
+        }))
+    }
+}
@@ -1,29 +1,39 @@
 use tonic::{Request, Response, Status};
 use maowbot_proto::maowbot::services::{command_service_server::CommandService, *};
 use maowbot_proto::maowbot::common;
+use maowbot_common::models::command::{format_role_cooldowns, parse_role_cooldowns};
 use maowbot_common::traits::repository_traits::{CommandRepository, CommandUsageRepository};
+use maowbot_core::repositories::Repositories;
+use maowbot_core::services::command_import::{self, ImportSource, ImportStatus};
 use std::sync::Arc;
 use std::collections::HashMap;
 use uuid::Uuid;
-use chrono::Utc;
-use tracing::{info, error, debug};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tracing::{info, debug};
 use prost_types;
+use super::paging::{sort_field, Page, ReadMask};
+use super::workspace::WorkspaceResolver;
+use crate::authz::{audit_value, AuditNote};
 
 pub struct CommandServiceImpl {
-    command_repo: Arc<dyn CommandRepository + Send + Sync>,
+    repos: Repositories,
     command_usage_repo: Arc<dyn CommandUsageRepository + Send + Sync>,
+    workspaces: WorkspaceResolver,
 }
 
 impl CommandServiceImpl {
-    pub fn new(
-        command_repo: Arc<dyn CommandRepository + Send + Sync>,
-        command_usage_repo: Arc<dyn CommandUsageRepository + Send + Sync>,
-    ) -> Self {
+    pub fn new(repos: Repositories, workspaces: WorkspaceResolver) -> Self {
         Self {
-            command_repo,
-            command_usage_repo,
+            command_usage_repo: repos.command_usage.clone(),
+            repos,
+            workspaces,
         }
     }
+
+    /// The command repository for the workspace selected in `request`'s metadata.
+    async fn command_repo_for<T>(&self, request: &Request<T>) -> Result<Arc<dyn CommandRepository + Send + Sync>, Status> {
+        Ok(self.repos.for_workspace(self.workspaces.resolve(request).await?).commands)
+    }
     
     fn command_to_proto(cmd: &maowbot_common::models::command::Command) -> common::Command {
         let mut metadata = std::collections::HashMap::new();
@@ -31,6 +41,14 @@ impl CommandServiceImpl {
         metadata.insert("cooldown_warn_once".to_string(), cmd.cooldown_warnonce.to_string());
         metadata.insert("stream_online_only".to_string(), cmd.stream_online_only.to_string());
         metadata.insert("stream_offline_only".to_string(), cmd.stream_offline_only.to_string());
+        metadata.insert("user_cooldown_seconds".to_string(), cmd.user_cooldown_seconds.to_string());
+        metadata.insert("role_cooldowns".to_string(), format_role_cooldowns(&cmd.role_cooldowns));
+        metadata.insert("cooldown_bypass_mods".to_string(), cmd.cooldown_bypass_mods.to_string());
+        metadata.insert("reply_privately".to_string(), cmd.reply_privately.to_string());
+        metadata.insert("reply_to_message".to_string(), cmd.reply_to_message.to_string());
+        if let Some(response) = &cmd.response {
+            metadata.insert("response".to_string(), response.clone());
+        }
         if let Some(cred_id) = &cmd.respond_with_credential {
             metadata.insert("respond_with_credential".to_string(), cred_id.to_string());
         }
@@ -67,8 +85,7 @@ impl CommandServiceImpl {
         };
         
         // Extract fields from metadata
-        let min_role = proto.metadata.get("min_role")
-            .map(|s| s.clone())
+        let min_role = proto.metadata.get("min_role").cloned()
             .unwrap_or_else(|| "Everyone".to_string());
             
         let cooldown_warnonce = proto.metadata.get("cooldown_warn_once")
@@ -88,6 +105,31 @@ impl CommandServiceImpl {
             
         let active_credential_id = proto.metadata.get("active_credential_id")
             .and_then(|id| Uuid::parse_str(id).ok());
+
+        let user_cooldown_seconds = proto.metadata.get("user_cooldown_seconds")
+            .and_then(|s| s.parse::<i32>().ok())
+            .unwrap_or(0);
+
+        let role_cooldowns = match proto.metadata.get("role_cooldowns") {
+            Some(s) => parse_role_cooldowns(s).map_err(|e| Status::invalid_argument(e.to_string()))?,
+            None => Default::default(),
+        };
+
+        let cooldown_bypass_mods = proto.metadata.get("cooldown_bypass_mods")
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        let reply_privately = proto.metadata.get("reply_privately")
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        let reply_to_message = proto.metadata.get("reply_to_message")
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        let response = proto.metadata.get("response")
+            .filter(|s| !s.trim().is_empty())
+            .cloned();
         
         Ok(maowbot_common::models::command::Command {
             command_id,
@@ -98,6 +140,12 @@ impl CommandServiceImpl {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             cooldown_seconds: proto.cooldown_seconds,
+            user_cooldown_seconds,
+            role_cooldowns,
+            cooldown_bypass_mods,
+            reply_privately,
+            reply_to_message,
+            response,
             cooldown_warnonce,
             respond_with_credential,
             stream_online_only,
@@ -110,15 +158,20 @@ impl CommandServiceImpl {
 #[tonic::async_trait]
 impl CommandService for CommandServiceImpl {
     async fn list_commands(&self, request: Request<ListCommandsRequest>) -> Result<Response<ListCommandsResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Listing commands - platform: {:?}, active_only: {}", req.platform, req.active_only);
+
+        let page = Page::from_request(req.page.as_ref())?;
+        let mask = ReadMask::parse(req.read_mask.as_ref(), &["command", "stats", "is_builtin"])?;
+        let order_by = sort_field(&req.order_by, &["name", "created_at", "updated_at"])?;
         
         // Get commands for platform
         let commands = if req.platform.is_empty() {
             // If no platform specified, we'd need to list all - for now return empty
             vec![]
         } else {
-            self.command_repo.list_commands(&req.platform).await
+            command_repo.list_commands(&req.platform).await
                 .map_err(|e| Status::internal(format!("Failed to list commands: {}", e)))?
         };
         
@@ -128,8 +181,19 @@ impl CommandService for CommandServiceImpl {
             .filter(|cmd| req.name_prefix.is_empty() || cmd.command_name.starts_with(&req.name_prefix))
             .collect();
         
-        // Sort by name
-        filtered_commands.sort_by(|a, b| a.command_name.cmp(&b.command_name));
+        // Name breaks ties so pages stay stable
+        filtered_commands.sort_by(|a, b| {
+            let ordering = match order_by {
+                "created_at" => a.created_at.cmp(&b.created_at),
+                "updated_at" => a.updated_at.cmp(&b.updated_at),
+                _ => std::cmp::Ordering::Equal,
+            };
+            ordering.then_with(|| a.command_name.cmp(&b.command_name))
+        });
+        if req.descending {
+            filtered_commands.reverse();
+        }
+        let (filtered_commands, page) = page.slice(filtered_commands);
         
         // Convert to proto format
         let mut command_infos = Vec::new();
@@ -149,26 +213,26 @@ impl CommandService for CommandServiceImpl {
                              cmd.command_name == "!followage");
             
             command_infos.push(CommandInfo {
-                command: Some(Self::command_to_proto(&cmd)),
-                stats: Some(stats),
-                is_builtin,
+                command: mask.includes("command").then(|| Self::command_to_proto(&cmd)),
+                stats: mask.includes("stats").then_some(stats),
+                is_builtin: mask.includes("is_builtin") && is_builtin,
             });
         }
         
-        // TODO: Implement proper pagination
         Ok(Response::new(ListCommandsResponse {
             commands: command_infos,
-            page: None,
+            page: Some(page),
         }))
     }
     async fn get_command(&self, request: Request<GetCommandRequest>) -> Result<Response<GetCommandResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         let command_id = Uuid::parse_str(&req.command_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid command ID: {}", e)))?;
         
         debug!("Getting command: {}", command_id);
         
-        let cmd = self.command_repo.get_command_by_id(command_id).await
+        let cmd = command_repo.get_command_by_id(command_id).await
             .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
         
         let cmd = match cmd {
@@ -200,7 +264,7 @@ impl CommandService for CommandServiceImpl {
         let stats = CommandStatistics {
             total_uses: recent_usage.len() as i64,
             unique_users: 0, // TODO: Calculate unique users
-            last_used: recent_usage.first().and_then(|u| u.used_at.clone()),
+            last_used: recent_usage.first().and_then(|u| u.used_at),
             average_cooldown_wait: 0.0,
         };
         
@@ -216,6 +280,8 @@ impl CommandService for CommandServiceImpl {
         }))
     }
     async fn create_command(&self, request: Request<CreateCommandRequest>) -> Result<Response<CreateCommandResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         let proto_cmd = req.command.ok_or_else(|| Status::invalid_argument("Command is required"))?;
         
@@ -227,7 +293,7 @@ impl CommandService for CommandServiceImpl {
         }
         
         // Check if command already exists
-        let existing = self.command_repo.get_command_by_name(&proto_cmd.platform, &proto_cmd.name).await
+        let existing = command_repo.get_command_by_name(&proto_cmd.platform, &proto_cmd.name).await
             .map_err(|e| Status::internal(format!("Failed to check existing command: {}", e)))?;
         
         if existing.is_some() {
@@ -248,14 +314,17 @@ impl CommandService for CommandServiceImpl {
         cmd.updated_at = Utc::now();
         
         // Create the command
-        self.command_repo.create_command(&cmd).await
+        command_repo.create_command(&cmd).await
             .map_err(|e| Status::internal(format!("Failed to create command: {}", e)))?;
+        audit.change(format!("command:{}/{}", cmd.platform, cmd.command_name), None, audit_value(&cmd));
         
         Ok(Response::new(CreateCommandResponse {
             command: Some(Self::command_to_proto(&cmd)),
         }))
     }
     async fn update_command(&self, request: Request<UpdateCommandRequest>) -> Result<Response<UpdateCommandResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         let command_id = Uuid::parse_str(&req.command_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid command ID: {}", e)))?;
@@ -263,13 +332,14 @@ impl CommandService for CommandServiceImpl {
         info!("Updating command: {}", command_id);
         
         // Get existing command
-        let existing = self.command_repo.get_command_by_id(command_id).await
+        let existing = command_repo.get_command_by_id(command_id).await
             .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
         
         let mut existing = match existing {
             Some(c) => c,
             None => return Err(Status::not_found("Command not found")),
         };
+        let before = audit_value(&existing);
         
         let proto_cmd = req.command.ok_or_else(|| Status::invalid_argument("Command is required"))?;
         
@@ -278,14 +348,32 @@ impl CommandService for CommandServiceImpl {
             for path in &mask.paths {
                 match path.as_str() {
                     "name" => existing.command_name = proto_cmd.name.clone(),
-                    "min_role" => existing.min_role = proto_cmd.metadata.get("min_role")
-                        .map(|s| s.clone())
+                    "min_role" => existing.min_role = proto_cmd.metadata.get("min_role").cloned()
                         .unwrap_or_else(|| existing.min_role.clone()),
                     "is_active" => existing.is_active = proto_cmd.is_active,
                     "cooldown_seconds" => existing.cooldown_seconds = proto_cmd.cooldown_seconds,
                     "cooldown_warn_once" => existing.cooldown_warnonce = proto_cmd.metadata.get("cooldown_warn_once")
                         .and_then(|s| s.parse::<bool>().ok())
                         .unwrap_or(existing.cooldown_warnonce),
+                    "user_cooldown_seconds" => existing.user_cooldown_seconds = proto_cmd.metadata.get("user_cooldown_seconds")
+                        .and_then(|s| s.parse::<i32>().ok())
+                        .unwrap_or(existing.user_cooldown_seconds),
+                    "role_cooldowns" => if let Some(s) = proto_cmd.metadata.get("role_cooldowns") {
+                        existing.role_cooldowns = parse_role_cooldowns(s)
+                            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+                    },
+                    "cooldown_bypass_mods" => existing.cooldown_bypass_mods = proto_cmd.metadata.get("cooldown_bypass_mods")
+                        .and_then(|s| s.parse::<bool>().ok())
+                        .unwrap_or(existing.cooldown_bypass_mods),
+                    "reply_privately" => existing.reply_privately = proto_cmd.metadata.get("reply_privately")
+                        .and_then(|s| s.parse::<bool>().ok())
+                        .unwrap_or(existing.reply_privately),
+                    "reply_to_message" => existing.reply_to_message = proto_cmd.metadata.get("reply_to_message")
+                        .and_then(|s| s.parse::<bool>().ok())
+                        .unwrap_or(existing.reply_to_message),
+                    "response" => existing.response = proto_cmd.metadata.get("response")
+                        .filter(|s| !s.trim().is_empty())
+                        .cloned(),
                     "stream_online_only" => existing.stream_online_only = proto_cmd.metadata.get("stream_online_only")
                         .and_then(|s| s.parse::<bool>().ok())
                         .unwrap_or(existing.stream_online_only),
@@ -320,44 +408,62 @@ impl CommandService for CommandServiceImpl {
         existing.updated_at = Utc::now();
         
         // Update the command
-        self.command_repo.update_command(&existing).await
+        command_repo.update_command(&existing).await
             .map_err(|e| Status::internal(format!("Failed to update command: {}", e)))?;
+        audit.change(
+            format!("command:{}/{}", existing.platform, existing.command_name),
+            before,
+            audit_value(&existing),
+        );
         
         Ok(Response::new(UpdateCommandResponse {
             command: Some(Self::command_to_proto(&existing)),
         }))
     }
     async fn delete_command(&self, request: Request<DeleteCommandRequest>) -> Result<Response<()>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         let command_id = Uuid::parse_str(&req.command_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid command ID: {}", e)))?;
         
         info!("Deleting command: {}", command_id);
         
+        let existing = command_repo.get_command_by_id(command_id).await
+            .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
+        
         if req.soft_delete {
             // Soft delete - just mark as inactive
-            let existing = self.command_repo.get_command_by_id(command_id).await
-                .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
-            
             let mut existing = match existing {
                 Some(c) => c,
                 None => return Err(Status::not_found("Command not found")),
             };
+            let before = audit_value(&existing);
             
             existing.is_active = false;
             existing.updated_at = Utc::now();
             
-            self.command_repo.update_command(&existing).await
+            command_repo.update_command(&existing).await
                 .map_err(|e| Status::internal(format!("Failed to soft delete command: {}", e)))?;
+            audit.change(
+                format!("command:{}/{}", existing.platform, existing.command_name),
+                before,
+                audit_value(&existing),
+            );
         } else {
             // Hard delete
-            self.command_repo.delete_command(command_id).await
+            command_repo.delete_command(command_id).await
                 .map_err(|e| Status::internal(format!("Failed to delete command: {}", e)))?;
+            let target = existing.as_ref()
+                .map(|c| format!("command:{}/{}", c.platform, c.command_name))
+                .unwrap_or_else(|| format!("command:{}", command_id));
+            audit.change(target, existing.as_ref().and_then(audit_value), None);
         }
         
         Ok(Response::new(()))
     }
     async fn batch_list_commands(&self, request: Request<BatchListCommandsRequest>) -> Result<Response<BatchListCommandsResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Batch listing commands for {} platforms", req.platforms.len());
         
@@ -366,7 +472,7 @@ impl CommandService for CommandServiceImpl {
         
         // Get commands for each platform
         for platform in &req.platforms {
-            let commands = self.command_repo.list_commands(platform).await
+            let commands = command_repo.list_commands(platform).await
                 .map_err(|e| Status::internal(format!("Failed to list commands for {}: {}", platform, e)))?;
             
             let active_count = commands.iter().filter(|c| c.is_active).count() as i32;
@@ -408,6 +514,7 @@ impl CommandService for CommandServiceImpl {
         }))
     }
     async fn batch_update_commands(&self, request: Request<BatchUpdateCommandsRequest>) -> Result<Response<BatchUpdateCommandsResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         info!("Batch updating {} commands", req.updates.len());
         
@@ -434,7 +541,7 @@ impl CommandService for CommandServiceImpl {
             };
             
             // Get existing command
-            let existing = match self.command_repo.get_command_by_id(command_id).await {
+            let existing = match command_repo.get_command_by_id(command_id).await {
                 Ok(Some(c)) => c,
                 Ok(None) => {
                     failure_count += 1;
@@ -472,14 +579,33 @@ impl CommandService for CommandServiceImpl {
                     for path in &mask.paths {
                         match path.as_str() {
                             "name" => updated.command_name = proto_cmd.name.clone(),
-                            "min_role" => updated.min_role = proto_cmd.metadata.get("min_role")
-                                .map(|s| s.clone())
+                            "min_role" => updated.min_role = proto_cmd.metadata.get("min_role").cloned()
                                 .unwrap_or_else(|| updated.min_role.clone()),
                             "is_active" => updated.is_active = proto_cmd.is_active,
                             "cooldown_seconds" => updated.cooldown_seconds = proto_cmd.cooldown_seconds,
                             "cooldown_warn_once" => updated.cooldown_warnonce = proto_cmd.metadata.get("cooldown_warn_once")
                                 .and_then(|s| s.parse::<bool>().ok())
                                 .unwrap_or(updated.cooldown_warnonce),
+                            "user_cooldown_seconds" => updated.user_cooldown_seconds = proto_cmd.metadata.get("user_cooldown_seconds")
+                                .and_then(|s| s.parse::<i32>().ok())
+                                .unwrap_or(updated.user_cooldown_seconds),
+                            "role_cooldowns" => if let Some(cooldowns) = proto_cmd.metadata.get("role_cooldowns")
+                                .and_then(|s| parse_role_cooldowns(s).ok())
+                            {
+                                updated.role_cooldowns = cooldowns;
+                            },
+                            "cooldown_bypass_mods" => updated.cooldown_bypass_mods = proto_cmd.metadata.get("cooldown_bypass_mods")
+                                .and_then(|s| s.parse::<bool>().ok())
+                                .unwrap_or(updated.cooldown_bypass_mods),
+                            "reply_privately" => updated.reply_privately = proto_cmd.metadata.get("reply_privately")
+                                .and_then(|s| s.parse::<bool>().ok())
+                                .unwrap_or(updated.reply_privately),
+                            "reply_to_message" => updated.reply_to_message = proto_cmd.metadata.get("reply_to_message")
+                                .and_then(|s| s.parse::<bool>().ok())
+                                .unwrap_or(updated.reply_to_message),
+                            "response" => updated.response = proto_cmd.metadata.get("response")
+                                .filter(|s| !s.trim().is_empty())
+                                .cloned(),
                             _ => {}
                         }
                     }
@@ -489,7 +615,7 @@ impl CommandService for CommandServiceImpl {
             updated.updated_at = Utc::now();
             
             // Save the update
-            match self.command_repo.update_command(&updated).await {
+            match command_repo.update_command(&updated).await {
                 Ok(_) => {
                     success_count += 1;
                     results.push(UpdateResult {
@@ -521,11 +647,12 @@ impl CommandService for CommandServiceImpl {
         }))
     }
     async fn execute_command(&self, request: Request<ExecuteCommandRequest>) -> Result<Response<ExecuteCommandResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Executing command: {} on platform {} for user {}", req.command_name, req.platform, req.user_id);
         
         // Get the command
-        let cmd = self.command_repo.get_command_by_name(&req.platform, &req.command_name).await
+        let cmd = command_repo.get_command_by_name(&req.platform, &req.command_name).await
             .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
         
         let cmd = match cmd {
@@ -575,6 +702,7 @@ impl CommandService for CommandServiceImpl {
         }))
     }
     async fn test_command(&self, request: Request<TestCommandRequest>) -> Result<Response<TestCommandResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         let command_id = Uuid::parse_str(&req.command_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid command ID: {}", e)))?;
@@ -582,7 +710,7 @@ impl CommandService for CommandServiceImpl {
         debug!("Testing command: {}", command_id);
         
         // Get the command
-        let cmd = self.command_repo.get_command_by_id(command_id).await
+        let cmd = command_repo.get_command_by_id(command_id).await
             .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
         
         let cmd = match cmd {
@@ -617,6 +745,7 @@ impl CommandService for CommandServiceImpl {
         }))
     }
     async fn get_command_usage(&self, request: Request<GetCommandUsageRequest>) -> Result<Response<GetCommandUsageResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Getting command usage");
         
@@ -628,7 +757,7 @@ impl CommandService for CommandServiceImpl {
                 .map_err(|e| Status::invalid_argument(format!("Invalid command ID: {}", e)))?;
             
             // Get the command first
-            let cmd = self.command_repo.get_command_by_id(command_id).await
+            let cmd = command_repo.get_command_by_id(command_id).await
                 .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?;
             
             if let Some(cmd) = cmd {
@@ -662,7 +791,7 @@ impl CommandService for CommandServiceImpl {
             }
         } else if !req.platform.is_empty() {
             // Get all commands for platform and their usage
-            let commands = self.command_repo.list_commands(&req.platform).await
+            let commands = command_repo.list_commands(&req.platform).await
                 .map_err(|e| Status::internal(format!("Failed to list commands: {}", e)))?;
             
             for cmd in commands {
@@ -712,9 +841,116 @@ impl CommandService for CommandServiceImpl {
             summary: Some(summary),
         }))
     }
+    async fn get_command_stats(&self, request: Request<GetCommandStatsRequest>) -> Result<Response<GetCommandStatsResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
+        let req = request.into_inner();
+        let command_id = Uuid::parse_str(&req.command_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid command ID: {}", e)))?;
+        let cmd = command_repo.get_command_by_id(command_id).await
+            .map_err(|e| Status::internal(format!("Failed to get command: {}", e)))?
+            .ok_or_else(|| Status::not_found("Command not found"))?;
+
+        let since = stats_since(req.since, Utc::now());
+        let stats = self.command_usage_repo.command_stats(command_id, since).await
+            .map_err(|e| Status::internal(format!("Failed to get command stats: {}", e)))?;
+
+        let timestamp = |t: DateTime<Utc>| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        };
+        Ok(Response::new(GetCommandStatsResponse {
+            command_id: command_id.to_string(),
+            command_name: cmd.command_name,
+            platform: cmd.platform,
+            since: Some(timestamp(since)),
+            uses: stats.uses,
+            errors: stats.errors,
+            timed_uses: stats.timed_uses,
+            avg_ms: stats.avg_ms,
+            p50_ms: stats.p50_ms,
+            p95_ms: stats.p95_ms,
+            p99_ms: stats.p99_ms,
+            max_ms: stats.max_ms,
+            last_used: stats.last_used_at.map(timestamp),
+            last_error: stats.last_error.unwrap_or_default(),
+        }))
+    }
+    async fn import_commands(&self, request: Request<ImportCommandsRequest>) -> Result<Response<ImportCommandsResponse>, Status> {
+        let command_repo = self.command_repo_for(&request).await?;
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let source: ImportSource = req.source.parse()
+            .map_err(|e| Status::invalid_argument(format!("{}", e)))?;
+        let platform = if req.platform.trim().is_empty() { "twitch-irc" } else { req.platform.trim() };
+
+        let plan = command_import::parse_export(source, &req.content)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let statuses = command_import::import_commands(command_repo.as_ref(), platform, &plan.commands, req.dry_run).await
+            .map_err(|e| Status::internal(format!("Failed to import commands: {}", e)))?;
+
+        let created: Vec<&str> = plan.commands.iter().zip(&statuses)
+            .filter(|(_, status)| **status == ImportStatus::Created)
+            .map(|(cmd, _)| cmd.name.as_str())
+            .collect();
+        info!("Imported {} of {} {} commands on {}{}", created.len(), plan.commands.len(), source, platform,
+            if req.dry_run { " (dry run)" } else { "" });
+        if !created.is_empty() {
+            audit.change(format!("commands:{}/import:{}", platform, source), None, audit_value(&created));
+        }
+
+        Ok(Response::new(ImportCommandsResponse {
+            commands: plan.commands.iter().zip(&statuses)
+                .map(|(cmd, status)| ImportedCommand {
+                    name: cmd.name.clone(),
+                    response: cmd.response.clone(),
+                    min_role: cmd.min_role.clone(),
+                    cooldown_seconds: cmd.cooldown_seconds,
+                    user_cooldown_seconds: cmd.user_cooldown_seconds,
+                    enabled: cmd.enabled,
+                    status: status.as_str().to_string(),
+                })
+                .collect(),
+            timers: plan.timers.iter()
+                .map(|t| ImportedTimer {
+                    name: t.name.clone(),
+                    messages: t.messages.clone(),
+                    interval_minutes: t.interval_minutes.unwrap_or(0),
+                    enabled: t.enabled,
+                })
+                .collect(),
+            skipped: plan.skipped,
+            mapped: plan.mapped.into_iter()
+                .map(|(variable, placeholder)| VariableMapping { variable, placeholder })
+                .collect(),
+            unsupported: plan.unsupported.into_iter()
+                .map(|(variable, used_by)| UnsupportedVariable { variable, used_by })
+                .collect(),
+            dry_run: req.dry_run,
+        }))
+    }
     type StreamCommandEventsStream = tonic::codec::Streaming<CommandEvent>;
     async fn stream_command_events(&self, _: Request<StreamCommandEventsRequest>) -> Result<Response<Self::StreamCommandEventsStream>, Status> {
         // TODO: Implement streaming of command events
         Err(Status::unimplemented("Command event streaming not yet implemented"))
     }
-}
\ No newline at end of file
+}
+
+/// Start of the stats window: the requested time, or the last week.
+fn stats_since(since: Option<prost_types::Timestamp>, now: DateTime<Utc>) -> DateTime<Utc> {
+    since
+        .and_then(|ts| Utc.timestamp_opt(ts.seconds, ts.nanos.max(0) as u32).single())
+        .unwrap_or(now - Duration::days(7))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_window_defaults_to_a_week() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        assert_eq!(stats_since(None, now), now - Duration::days(7));
+        let asked = prost_types::Timestamp { seconds: now.timestamp() - 3600, nanos: 0 };
+        assert_eq!(stats_since(Some(asked), now), now - Duration::hours(1));
+    }
+}
@@ -1,26 +1,333 @@
 use tonic::{Request, Response, Status};
 use maowbot_proto::maowbot::services::{config_service_server::ConfigService, *};
 use maowbot_proto::maowbot::common::CacheControl;
-use maowbot_core::repositories::postgres::bot_config::PostgresBotConfigRepository;
 use maowbot_common::traits::repository_traits::BotConfigRepository;
+use maowbot_common::models::workspace as ws_model;
+use maowbot_common::models::settings::{SettingDefinition, SettingType};
+use maowbot_core::settings::SettingsRegistry;
+use maowbot_core::updater::{self, UpdatePhase, Updater};
 use maowbot_core::eventbus::EventBus;
+use maowbot_core::crypto::{Encryptor, rotation::rotate_master_key, secrets::SecretsManager};
+use maowbot_core::db::{backup, transfer::BackendRepos};
+use maowbot_core::repositories::Repositories;
+use maowbot_core::services::workspace_router::WorkspaceRouter;
+use tokio::sync::Mutex;
 use std::sync::Arc;
 use std::collections::HashMap;
 use chrono::Utc;
 use tracing::{info, error, debug, warn};
 use prost_types;
 use serde_json;
+use super::workspace::WorkspaceResolver;
+use crate::authz::{AuditNote, Caller, TokenStore};
+use crate::db_maintenance::{self, DbMaintenance};
+use maowbot_core::tasks::retention::{self, RetentionEngine};
+use crate::logging::{self, LogLevels};
+use crate::tls;
+use maowbot_common::models::api_token::{self as token_model, ApiRole, Permission};
 
 pub struct ConfigServiceImpl {
-    bot_config_repo: Arc<PostgresBotConfigRepository>,
+    repos: Repositories,
+    settings: Arc<SettingsRegistry>,
+    updater: Arc<Updater>,
+    db_maintenance: Arc<DbMaintenance>,
+    retention: Arc<RetentionEngine>,
     event_bus: Arc<EventBus>,
+    secrets: Arc<Mutex<SecretsManager>>,
+    encryptor: Encryptor,
+    workspaces: WorkspaceResolver,
+    workspace_router: Arc<WorkspaceRouter>,
+    api_tokens: TokenStore,
 }
 
 impl ConfigServiceImpl {
-    pub fn new(bot_config_repo: Arc<PostgresBotConfigRepository>, event_bus: Arc<EventBus>) -> Self {
-        Self { bot_config_repo, event_bus }
+    pub fn new(
+        repos: Repositories,
+        settings: Arc<SettingsRegistry>,
+        updater: Arc<Updater>,
+        db_maintenance: Arc<DbMaintenance>,
+        retention: Arc<RetentionEngine>,
+        event_bus: Arc<EventBus>,
+        secrets: Arc<Mutex<SecretsManager>>,
+        encryptor: Encryptor,
+        workspaces: WorkspaceResolver,
+        workspace_router: Arc<WorkspaceRouter>,
+        api_tokens: TokenStore,
+    ) -> Self {
+        Self { repos, settings, updater, db_maintenance, retention, event_bus, secrets, encryptor, workspaces, workspace_router, api_tokens }
+    }
+
+    /// Rejects values that don't match a known setting's type or range.
+    fn validate_setting(key: &str, value: &str) -> Result<(), Status> {
+        SettingsRegistry::validate(key, value).map_err(|e| Status::invalid_argument(e.to_string()))
+    }
+
+    /// Applies writes to the default workspace right away (the registry only
+    /// tracks that one) instead of waiting for the next background reload.
+    async fn apply_settings(&self, workspace_id: uuid::Uuid) {
+        if workspace_id == ws_model::DEFAULT_WORKSPACE_ID {
+            if let Err(e) = self.settings.refresh().await {
+                error!("Failed to reload settings: {:?}", e);
+            }
+        }
+    }
+
+    /// Metadata for a key the settings registry knows about.
+    fn setting_metadata(key: &str) -> Option<ConfigMetadata> {
+        SettingsRegistry::definition(key).map(Self::definition_to_metadata)
+    }
+
+    /// Whether `key` holds a secret: the settings schema says so, or the name looks like one.
+    fn is_secret_key(key: &str) -> bool {
+        Self::setting_metadata(key).map(|m| m.is_secret).unwrap_or(false)
+            || ["secret", "password", "token", "api_key"].iter().any(|s| key.contains(s))
+    }
+
+    /// A secret's value as reads show it.
+    fn masked(key: &str, value: String) -> String {
+        if Self::is_secret_key(key) { "<redacted>".to_string() } else { value }
+    }
+
+    /// Refuses `include_secrets` to anything but admin tokens. Calls that didn't
+    /// come through the authz layer (the in-process console) are trusted.
+    fn check_secrets_allowed<T>(request: &Request<T>, include_secrets: bool) -> Result<(), Status> {
+        let admin = request.extensions().get::<Caller>().is_none_or(|c| c.role.grants(Permission::Admin));
+        if include_secrets && !admin {
+            return Err(Status::permission_denied("include_secrets requires admin permission"));
+        }
+        Ok(())
+    }
+
+    /// Stores a value, encrypted when the settings schema marks the key secret.
+    async fn store_value(repo: &Arc<dyn BotConfigRepository + Send + Sync>, key: &str, value: &str) -> Result<(), maowbot_core::Error> {
+        if SettingsRegistry::is_secret(key) {
+            repo.set_secret(key, value).await
+        } else {
+            repo.set_value(key, value).await
+        }
+    }
+
+    /// Every plain config value, plus the decrypted secrets when they're asked for.
+    async fn read_configs(
+        repo: &Arc<dyn BotConfigRepository + Send + Sync>,
+        include_secrets: bool,
+    ) -> Result<Vec<(String, String)>, Status> {
+        let failed = |e: maowbot_core::Error| Status::internal(format!("Failed to list configs: {}", e));
+        let mut configs = repo.list_all().await.map_err(failed)?;
+        if include_secrets {
+            for key in repo.list_secret_keys().await.map_err(failed)? {
+                if let Some(value) = repo.get_secret(&key).await.map_err(failed)? {
+                    configs.push((key, value));
+                }
+            }
+        }
+        Ok(configs)
+    }
+
+    /// A config value as the audit log shows it; secrets only show that they were set.
+    fn audit_config_value(key: &str, value: Option<&str>, is_secret: bool) -> Option<serde_json::Value> {
+        let secret = is_secret || Self::is_secret_key(key);
+        value.map(|v| serde_json::Value::String(if secret { "<redacted>".to_string() } else { v.to_string() }))
+    }
+
+    fn definition_to_metadata(def: &SettingDefinition) -> ConfigMetadata {
+        let config_type = match def.setting_type {
+            SettingType::String | SettingType::Enum => ConfigType::String,
+            SettingType::Integer => ConfigType::Integer,
+            SettingType::Float => ConfigType::Float,
+            SettingType::Boolean => ConfigType::Boolean,
+            SettingType::Json => ConfigType::Json,
+        };
+        ConfigMetadata {
+            description: def.description.to_string(),
+            r#type: config_type as i32,
+            default_value: def.default.unwrap_or_default().to_string(),
+            allowed_values: def.allowed_values.iter().map(|v| v.to_string()).collect(),
+            is_secret: def.is_secret,
+            is_required: false,
+            category: def.category.to_string(),
+            created_at: None,
+            updated_at: None,
+            updated_by: String::new(),
+        }
+    }
+
+    /// The config repository for the workspace selected in `request`'s metadata.
+    async fn bot_config_repo_for<T>(&self, request: &Request<T>) -> Result<Arc<dyn BotConfigRepository + Send + Sync>, Status> {
+        Ok(self.workspace_config_for(request).await?.1)
+    }
+
+    /// Like `bot_config_repo_for`, with the selected workspace for `apply_settings`.
+    async fn workspace_config_for<T>(
+        &self,
+        request: &Request<T>,
+    ) -> Result<(uuid::Uuid, Arc<dyn BotConfigRepository + Send + Sync>), Status> {
+        let workspace_id = self.workspaces.resolve(request).await?;
+        Ok((workspace_id, self.repos.for_workspace(workspace_id).bot_config))
+    }
+
+    fn workspace_to_proto(ws: &ws_model::Workspace) -> Workspace {
+        Workspace {
+            workspace_id: ws.workspace_id.to_string(),
+            name: ws.name.clone(),
+            display_name: ws.display_name.clone().unwrap_or_default(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: ws.created_at.timestamp(),
+                nanos: ws.created_at.timestamp_subsec_nanos() as i32,
+            }),
+            is_default: ws.is_default(),
+        }
     }
     
+    /// The only workspace the caller's token may see, if it's limited to one.
+    fn caller_workspace<T>(request: &Request<T>) -> Option<uuid::Uuid> {
+        request.extensions().get::<Caller>().and_then(|c| c.workspace_id)
+    }
+
+    /// The workspace named by `selector`, a name or UUID.
+    async fn find_workspace(&self, selector: &str) -> Result<ws_model::Workspace, Status> {
+        let selector = selector.trim();
+        let repo = self.workspaces.repo();
+        match uuid::Uuid::parse_str(selector) {
+            Ok(id) => repo.get_workspace(id).await,
+            Err(_) => repo.get_workspace_by_name(&selector.to_lowercase()).await,
+        }
+        .map_err(|e| Status::internal(format!("Failed to look up workspace: {}", e)))?
+        .ok_or_else(|| Status::not_found(format!("Workspace '{}' not found", selector)))
+    }
+
+    async fn workspace_names(&self) -> Result<HashMap<uuid::Uuid, String>, Status> {
+        Ok(self.workspaces.repo().list_workspaces().await
+            .map_err(|e| Status::internal(format!("Failed to list workspaces: {}", e)))?
+            .into_iter()
+            .map(|ws| (ws.workspace_id, ws.name))
+            .collect())
+    }
+
+    fn api_token_to_proto(token: &token_model::ApiToken, workspace_names: &HashMap<uuid::Uuid, String>) -> ApiToken {
+        let ts = |t: chrono::DateTime<Utc>| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        };
+        ApiToken {
+            token_id: token.token_id.to_string(),
+            name: token.name.clone(),
+            role: token.role.to_string(),
+            created_at: Some(ts(token.created_at)),
+            last_used_at: token.last_used_at.map(ts),
+            revoked_at: token.revoked_at.map(ts),
+            workspace: token.workspace_id
+                .map(|id| workspace_names.get(&id).cloned().unwrap_or_else(|| id.to_string()))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn log_overrides_to_proto(levels: &LogLevels) -> Vec<LogLevelOverride> {
+        levels.overrides.iter()
+            .map(|(module, level)| LogLevelOverride { module: module.clone(), level: level.clone() })
+            .collect()
+    }
+
+    fn backup_counts_to_proto(counts: &backup::BackupCounts) -> BackupCounts {
+        BackupCounts {
+            workspaces: counts.workspaces as u32,
+            users: counts.users as u32,
+            identities: counts.identities as u32,
+            platform_configs: counts.platform_configs as u32,
+            credentials: counts.credentials as u32,
+            bot_config: counts.bot_config as u32,
+            commands: counts.commands as u32,
+            redeems: counts.redeems as u32,
+            pipelines: counts.pipelines as u32,
+        }
+    }
+
+    /// Repositories over the whole database (every workspace), for backups.
+    fn backup_repos(&self) -> BackendRepos {
+        BackendRepos::from_repositories(&self.repos)
+    }
+
+    fn update_status_to_proto(&self) -> UpdateStatus {
+        let status = self.updater.status();
+        let ts = |t: chrono::DateTime<Utc>| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        };
+        let latest = status.latest.as_ref();
+        UpdateStatus {
+            phase: status.phase.to_string(),
+            latest_version: latest.map(|r| r.version.clone()).unwrap_or_default(),
+            release_notes: latest.map(|r| r.notes.clone()).unwrap_or_default(),
+            published_at: latest.and_then(|r| r.published_at).map(ts),
+            last_checked: status.last_checked.map(ts),
+            last_error: status.last_error.clone().unwrap_or_default(),
+            update_available: latest.map(|r| updater::is_newer(&r.version, &status.current_version)).unwrap_or(false)
+                && status.phase != UpdatePhase::Staged,
+            auto_update: self.settings.get_bool("updater.auto_update").unwrap_or(false),
+            feed_url: self.settings.get("updater.feed_url").unwrap_or_default(),
+            has_public_key: self.settings.get("updater.public_key").map(|k| !k.trim().is_empty()).unwrap_or(false),
+            target: updater::current_target(),
+            current_version: status.current_version,
+        }
+    }
+
+    fn timestamp(t: chrono::DateTime<Utc>) -> prost_types::Timestamp {
+        prost_types::Timestamp { seconds: t.timestamp(), nanos: t.timestamp_subsec_nanos() as i32 }
+    }
+
+    fn snapshot_to_proto(s: &db_maintenance::SnapshotInfo) -> DatabaseSnapshot {
+        DatabaseSnapshot {
+            name: s.name.clone(),
+            size_bytes: s.size_bytes as i64,
+            created_at: Some(Self::timestamp(s.created_at)),
+        }
+    }
+
+    fn integrity_to_proto(r: &db_maintenance::IntegrityReport) -> DatabaseIntegrityReport {
+        DatabaseIntegrityReport {
+            ok: r.ok(),
+            errors: r.errors.clone(),
+            warnings: r.warnings.clone(),
+            checked_at: Some(Self::timestamp(r.checked_at)),
+        }
+    }
+
+    fn retention_to_proto(r: &retention::RetentionReport) -> RetentionReport {
+        RetentionReport {
+            dry_run: r.dry_run,
+            tables: r.entries.iter().map(|e| RetentionTableReport {
+                table: e.policy.target.table().to_string(),
+                max_age_days: e.policy.max_age_days.unwrap_or(0),
+                max_rows: e.policy.max_rows.unwrap_or(0),
+                total_rows: e.total_rows,
+                deleted_by_age: e.by_age as i64,
+                deleted_by_size: e.by_size as i64,
+            }).collect(),
+            errors: r.errors.clone(),
+            ran_at: Some(Self::timestamp(r.ran_at)),
+        }
+    }
+
+    fn certificate_info_to_proto(&self) -> CertificateInfo {
+        let info = tls::server_cert_info();
+        CertificateInfo {
+            sans: info.as_ref().map(|i| i.sans.clone()).unwrap_or_default(),
+            generated_at: info.as_ref().map(|i| Self::timestamp(i.generated_at)),
+            fingerprint: info.as_ref().map(|i| i.fingerprint.clone()).unwrap_or_default(),
+            has_ca: tls::has_ca(),
+            require_client_cert: self.settings.get_bool("tls.require_client_cert").unwrap_or(false),
+            restart_required: info.as_ref().is_some_and(|i| i.restart_required()),
+        }
+    }
+
+    fn update_error_to_status(e: maowbot_core::Error) -> Status {
+        match e {
+            maowbot_core::Error::NotFound(msg) | maowbot_core::Error::ValidationError(msg) => Status::failed_precondition(msg),
+            maowbot_core::Error::Http(e) => Status::unavailable(format!("Release feed unreachable: {}", e)),
+            other => Status::internal(other.to_string()),
+        }
+    }
+
     fn value_to_config_type(value: &str) -> ConfigType {
         // Try to detect the type from the value
         if value == "true" || value == "false" {
@@ -44,43 +351,22 @@ impl ConfigServiceImpl {
 #[tonic::async_trait]
 impl ConfigService for ConfigServiceImpl {
     async fn get_config(&self, request: Request<GetConfigRequest>) -> Result<Response<GetConfigResponse>, Status> {
+        let bot_config_repo = self.bot_config_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Getting config for key: {}", req.key);
         
         // Get the value (metadata not supported in current API)
-        let value = match self.bot_config_repo.get_value(&req.key).await {
+        let value = match bot_config_repo.get_value(&req.key).await {
             Ok(Some(v)) => v,
             Ok(None) => return Err(Status::not_found(format!("Config key '{}' not found", req.key))),
             Err(e) => return Err(Status::internal(format!("Failed to get config: {}", e))),
         };
-        let meta: Option<serde_json::Value> = None; // TODO: Implement metadata support
-        
-        // Build metadata from JSON if available
-        let metadata = if let Some(json_meta) = meta {
-            let desc = json_meta.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
-            let category = json_meta.get("category").and_then(|v| v.as_str()).unwrap_or("").to_string();
-            let is_secret = json_meta.get("is_secret").and_then(|v| v.as_bool()).unwrap_or(false);
-            let is_required = json_meta.get("is_required").and_then(|v| v.as_bool()).unwrap_or(false);
-            
-            Some(ConfigMetadata {
-                description: desc,
-                r#type: Self::value_to_config_type(&value) as i32,
-                default_value: String::new(),
-                allowed_values: vec![],
-                is_secret,
-                is_required,
-                category,
-                created_at: None,
-                updated_at: None,
-                updated_by: String::new(),
-            })
-        } else {
-            None
-        };
+        // Known settings carry their schema; other keys have no metadata
+        let metadata = Self::setting_metadata(&req.key);
         
         let config_entry = ConfigEntry {
-            key: req.key.clone(),
-            value,
+            value: Self::masked(&req.key, value),
+            key: req.key,
             metadata,
         };
         
@@ -94,14 +380,23 @@ impl ConfigService for ConfigServiceImpl {
         }))
     }
     async fn set_config(&self, request: Request<SetConfigRequest>) -> Result<Response<SetConfigResponse>, Status> {
+        let (workspace_id, bot_config_repo) = self.workspace_config_for(&request).await?;
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Setting config for key: {}", req.key);
         
         // Get the previous value if it exists
-        let previous_value = self.bot_config_repo.get_value(&req.key).await
+        let previous_value = if SettingsRegistry::is_secret(&req.key) {
+            bot_config_repo.get_secret(&req.key).await
+        } else {
+            bot_config_repo.get_value(&req.key).await
+        }
             .map_err(|e| Status::internal(format!("Failed to get previous value: {}", e)))?;
         
         let was_created = previous_value.is_none();
+        let shown_previous = previous_value.clone().map(|v| Self::masked(&req.key, v)).unwrap_or_default();
+
+        Self::validate_setting(&req.key, &req.value)?;
         
         if req.validate_only {
             // Just validate without saving
@@ -123,7 +418,7 @@ impl ConfigService for ConfigServiceImpl {
                     }),
                 }),
                 was_created,
-                previous_value: previous_value.unwrap_or_default(),
+                previous_value: shown_previous,
             }));
         }
         
@@ -149,19 +444,30 @@ impl ConfigService for ConfigServiceImpl {
         };
         
         // Save the config
-        // If metadata is provided, use set_value_kv_meta, otherwise use simple set_value
-        if meta_json.is_some() {
-            self.bot_config_repo.set_value_kv_meta(&req.key, &req.value, meta_json).await
+        // Secrets are stored encrypted; otherwise metadata goes through set_value_kv_meta
+        let is_secret = req.metadata.as_ref().map(|m| m.is_secret).unwrap_or(false)
+            || SettingsRegistry::is_secret(&req.key);
+        if is_secret {
+            bot_config_repo.set_secret(&req.key, &req.value).await
+                .map_err(|e| Status::internal(format!("Failed to set config: {}", e)))?;
+        } else if meta_json.is_some() {
+            bot_config_repo.set_value_kv_meta(&req.key, &req.value, meta_json).await
                 .map_err(|e| Status::internal(format!("Failed to set config: {}", e)))?;
         } else {
             // For simple key-value pairs without metadata, use set_value
-            self.bot_config_repo.set_value(&req.key, &req.value).await
+            bot_config_repo.set_value(&req.key, &req.value).await
                 .map_err(|e| Status::internal(format!("Failed to set config: {}", e)))?;
         }
+        self.apply_settings(workspace_id).await;
+        audit.change(
+            format!("config:{}", req.key),
+            Self::audit_config_value(&req.key, previous_value.as_deref(), is_secret),
+            Self::audit_config_value(&req.key, Some(&req.value), is_secret),
+        );
         
         // Build response
         let now = Utc::now();
-        let metadata = req.metadata.map(|m| ConfigMetadata {
+        let metadata = req.metadata.or_else(|| Self::setting_metadata(&req.key)).map(|m| ConfigMetadata {
             description: m.description,
             r#type: m.r#type,
             default_value: m.default_value,
@@ -191,25 +497,36 @@ impl ConfigService for ConfigServiceImpl {
                 metadata,
             }),
             was_created,
-            previous_value: previous_value.unwrap_or_default(),
+            previous_value: shown_previous,
         }))
     }
     async fn delete_config(&self, request: Request<DeleteConfigRequest>) -> Result<Response<()>, Status> {
+        let (workspace_id, bot_config_repo) = self.workspace_config_for(&request).await?;
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Deleting config for key: {}", req.key);
         
-        self.bot_config_repo.delete_value(&req.key).await
+        let previous_value = bot_config_repo.get_value(&req.key).await
+            .map_err(|e| Status::internal(format!("Failed to get previous value: {}", e)))?;
+        bot_config_repo.delete_value(&req.key).await
             .map_err(|e| Status::internal(format!("Failed to delete config: {}", e)))?;
+        self.apply_settings(workspace_id).await;
+        audit.change(
+            format!("config:{}", req.key),
+            Self::audit_config_value(&req.key, previous_value.as_deref(), false),
+            None,
+        );
         
         Ok(Response::new(()))
     }
     async fn list_configs(&self, request: Request<ListConfigsRequest>) -> Result<Response<ListConfigsResponse>, Status> {
+        let bot_config_repo = self.bot_config_repo_for(&request).await?;
+        Self::check_secrets_allowed(&request, request.get_ref().include_secrets)?;
         let req = request.into_inner();
         debug!("Listing configs");
         
         // Get all configs
-        let all_configs = self.bot_config_repo.list_all().await
-            .map_err(|e| Status::internal(format!("Failed to list configs: {}", e)))?;
+        let all_configs = Self::read_configs(&bot_config_repo, req.include_secrets).await?;
         
         let mut config_entries = Vec::new();
         
@@ -219,52 +536,20 @@ impl ConfigService for ConfigServiceImpl {
                 continue;
             }
             
-            // Parse metadata if available
-            let metadata = if req.include_metadata {
-                // TODO: Implement metadata support
-                let json_meta: Option<serde_json::Value> = None;
-                if let Some(json_meta) = json_meta {
-                    let desc = json_meta.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                    let category = json_meta.get("category").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                    let is_secret = json_meta.get("is_secret").and_then(|v| v.as_bool()).unwrap_or(false);
-                    let is_required = json_meta.get("is_required").and_then(|v| v.as_bool()).unwrap_or(false);
-                    
-                    // Filter by category if specified
-                    if !req.categories.is_empty() && !req.categories.contains(&category) {
-                        continue;
-                    }
-                    
-                    // Skip secrets if not requested
-                    if is_secret && !req.include_secrets {
-                        continue;
-                    }
-                    
-                    Some(ConfigMetadata {
-                        description: desc,
-                        r#type: Self::value_to_config_type(&value) as i32,
-                        default_value: String::new(),
-                        allowed_values: vec![],
-                        is_secret,
-                        is_required,
-                        category,
-                        created_at: None,
-                        updated_at: None,
-                        updated_by: String::new(),
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+            // Category and secrecy come from the settings schema
+            let known = Self::setting_metadata(&key);
+            let category = known.as_ref().map(|m| m.category.clone()).unwrap_or_default();
+            if !req.categories.is_empty() && !req.categories.contains(&category) {
+                continue;
+            }
+            if Self::is_secret_key(&key) && !req.include_secrets {
+                continue;
+            }
+            let metadata = if req.include_metadata { known } else { None };
             
             config_entries.push(ConfigEntry {
                 key,
-                value: if metadata.as_ref().map(|m| m.is_secret).unwrap_or(false) && !req.include_secrets {
-                    "<redacted>".to_string()
-                } else {
-                    value
-                },
+                value,
                 metadata,
             });
         }
@@ -276,6 +561,7 @@ impl ConfigService for ConfigServiceImpl {
         }))
     }
     async fn batch_get_configs(&self, request: Request<BatchGetConfigsRequest>) -> Result<Response<BatchGetConfigsResponse>, Status> {
+        let bot_config_repo = self.bot_config_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Batch getting {} configs", req.keys.len());
         
@@ -283,39 +569,19 @@ impl ConfigService for ConfigServiceImpl {
         let mut not_found_keys = Vec::new();
         
         for key in &req.keys {
-            let result = self.bot_config_repo.get_value(key).await;
+            let result = bot_config_repo.get_value(key).await;
             
             match result {
                 Ok(Some(value)) => {
                     let metadata = if req.include_metadata {
-                        // TODO: Implement metadata support
-                        let json_meta: Option<serde_json::Value> = None;
-                        json_meta.and_then(|json_meta| {
-                            let desc = json_meta.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                            let category = json_meta.get("category").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                            let is_secret = json_meta.get("is_secret").and_then(|v| v.as_bool()).unwrap_or(false);
-                            let is_required = json_meta.get("is_required").and_then(|v| v.as_bool()).unwrap_or(false);
-                            
-                            Some(ConfigMetadata {
-                                description: desc,
-                                r#type: Self::value_to_config_type(&value) as i32,
-                                default_value: String::new(),
-                                allowed_values: vec![],
-                                is_secret,
-                                is_required,
-                                category,
-                                created_at: None,
-                                updated_at: None,
-                                updated_by: String::new(),
-                            })
-                        })
+                        Self::setting_metadata(key)
                     } else {
                         None
                     };
                     
                     configs.insert(key.clone(), ConfigEntry {
                         key: key.clone(),
-                        value,
+                        value: Self::masked(key, value),
                         metadata,
                     });
                 }
@@ -335,6 +601,7 @@ impl ConfigService for ConfigServiceImpl {
         }))
     }
     async fn batch_set_configs(&self, request: Request<BatchSetConfigsRequest>) -> Result<Response<BatchSetConfigsResponse>, Status> {
+        let (workspace_id, bot_config_repo) = self.workspace_config_for(&request).await?;
         let req = request.into_inner();
         info!("Batch setting {} configs", req.configs.len());
         
@@ -344,17 +611,20 @@ impl ConfigService for ConfigServiceImpl {
         
         // If atomic is true and validate_all is true, validate all first
         if req.atomic && req.validate_all {
-            // For now, we'll just check that all values are non-empty
             for (key, value) in &req.configs {
                 if key.is_empty() || value.is_empty() {
                     return Err(Status::invalid_argument(format!("Invalid config: key='{}', value='{}'" , key, value)));
                 }
+                Self::validate_setting(key, value)?;
             }
         }
         
         // Process each config
         for (key, value) in &req.configs {
-            let result = self.bot_config_repo.set_value(key, value).await;
+            let result = match SettingsRegistry::validate(key, value) {
+                Ok(()) => Self::store_value(&bot_config_repo, key, value).await,
+                Err(e) => Err(e),
+            };
             
             match result {
                 Ok(_) => {
@@ -366,7 +636,7 @@ impl ConfigService for ConfigServiceImpl {
                         config: Some(ConfigEntry {
                             key: key.clone(),
                             value: value.clone(),
-                            metadata: None,
+                            metadata: Self::setting_metadata(key),
                         }),
                     });
                 }
@@ -377,6 +647,7 @@ impl ConfigService for ConfigServiceImpl {
                     if req.atomic {
                         // If atomic, rollback all successful operations
                         // Since we don't have transaction support, we'll just fail
+                        self.apply_settings(workspace_id).await;
                         return Err(Status::internal(format!("Atomic operation failed at key '{}': {}", key, e)));
                     }
                     
@@ -390,6 +661,8 @@ impl ConfigService for ConfigServiceImpl {
             }
         }
         
+        self.apply_settings(workspace_id).await;
+
         Ok(Response::new(BatchSetConfigsResponse {
             results,
             success_count,
@@ -422,6 +695,16 @@ impl ConfigService for ConfigServiceImpl {
             });
         }
         
+        // Known settings are checked against their schema (type, range, allowed values)
+        if let Err(e) = SettingsRegistry::validate(&req.key, &req.value) {
+            is_valid = false;
+            errors.push(ValidationError {
+                field: "value".to_string(),
+                message: e.to_string(),
+                severity: 2,
+            });
+        }
+
         // Detect the actual type
         let detected_type = Self::value_to_config_type(&req.value);
         
@@ -441,18 +724,15 @@ impl ConfigService for ConfigServiceImpl {
         }
         
         // Additional type-specific validation
-        match detected_type {
-            ConfigType::Json => {
-                if serde_json::from_str::<serde_json::Value>(&req.value).is_err() {
-                    is_valid = false;
-                    errors.push(ValidationError {
-                        field: "value".to_string(),
-                        message: "Invalid JSON format".to_string(),
-                        severity: 2,
-                    });
-                }
+        if detected_type == ConfigType::Json {
+            if serde_json::from_str::<serde_json::Value>(&req.value).is_err() {
+                is_valid = false;
+                errors.push(ValidationError {
+                    field: "value".to_string(),
+                    message: "Invalid JSON format".to_string(),
+                    severity: 2,
+                });
             }
-            _ => {}
         }
         
         Ok(Response::new(ValidateConfigResponse {
@@ -472,12 +752,13 @@ impl ConfigService for ConfigServiceImpl {
         }))
     }
     async fn export_configs(&self, request: Request<ExportConfigsRequest>) -> Result<Response<ExportConfigsResponse>, Status> {
+        let bot_config_repo = self.bot_config_repo_for(&request).await?;
+        Self::check_secrets_allowed(&request, request.get_ref().include_secrets)?;
         let req = request.into_inner();
         info!("Exporting configs");
         
         // Get all configs
-        let all_configs = self.bot_config_repo.list_all().await
-            .map_err(|e| Status::internal(format!("Failed to list configs: {}", e)))?;
+        let all_configs = Self::read_configs(&bot_config_repo, req.include_secrets).await?;
         
         let mut export_data = HashMap::new();
         let mut config_count = 0;
@@ -485,10 +766,8 @@ impl ConfigService for ConfigServiceImpl {
         for (key, value) in all_configs {
             // TODO: Implement category filtering when metadata is available
             
-            // Skip secrets unless explicitly included (for now, skip anything with "secret" in the key)
-            let is_secret = key.contains("secret") || key.contains("password") || key.contains("token");
-            
-            if is_secret && !req.include_secrets {
+            // Skip secrets unless explicitly included
+            if Self::is_secret_key(&key) && !req.include_secrets {
                 continue;
             }
             
@@ -525,10 +804,11 @@ impl ConfigService for ConfigServiceImpl {
         Ok(Response::new(ExportConfigsResponse {
             data: export_result.into_bytes(),
             filename: format!("maowbot_config_export_{}.json", Utc::now().format("%Y%m%d_%H%M%S")),
-            config_count: config_count as i32,
+            config_count,
         }))
     }
     async fn import_configs(&self, request: Request<ImportConfigsRequest>) -> Result<Response<ImportConfigsResponse>, Status> {
+        let (workspace_id, bot_config_repo) = self.workspace_config_for(&request).await?;
         let req = request.into_inner();
         let import_mode = req.mode();
         let import_format = req.format();
@@ -557,18 +837,23 @@ impl ConfigService for ConfigServiceImpl {
                 .and_then(|v| v.as_str())
                 .ok_or_else(|| Status::invalid_argument(format!("Missing value for key: {}", key)))?;
             
-            let meta_json = config_data.get("metadata").cloned();
+            let _meta_json = config_data.get("metadata").cloned();
             
             // Check if we should skip existing configs
             if import_mode == ImportMode::Merge {
-                if let Ok(Some(_)) = self.bot_config_repo.get_value(&key).await {
+                if let Ok(Some(_)) = bot_config_repo.get_value(&key).await {
                     skipped += 1;
                     continue;
                 }
             }
             
-            // Import the config (metadata not supported in set_value)
-            match self.bot_config_repo.set_value(&key, value).await {
+            if let Err(e) = SettingsRegistry::validate(&key, value) {
+                errors.push(format!("Failed to import {}: {}", key, e));
+                continue;
+            }
+
+            // Import the config (metadata isn't imported)
+            match Self::store_value(&bot_config_repo, &key, value).await {
                 Ok(_) => imported += 1,
                 Err(e) => {
                     errors.push(format!("Failed to import {}: {}", key, e));
@@ -576,6 +861,8 @@ impl ConfigService for ConfigServiceImpl {
             }
         }
         
+        self.apply_settings(workspace_id).await;
+
         Ok(Response::new(ImportConfigsResponse {
             imported_count: imported,
             updated_count: 0, // TODO: Track updates separately
@@ -620,4 +907,467 @@ impl ConfigService for ConfigServiceImpl {
             }),
         }))
     }
-}
\ No newline at end of file
+
+    async fn rotate_encryption_key(&self, request: Request<RotateEncryptionKeyRequest>) -> Result<Response<RotateEncryptionKeyResponse>, Status> {
+        let req = request.into_inner();
+        info!("Encryption key rotation requested (dry_run: {})", req.dry_run);
+
+        // Holding the lock serializes rotations
+        let mut secrets = self.secrets.lock().await;
+        let key_source = secrets.source().to_string();
+
+        match rotate_master_key(self.repos.key_rotation.as_ref(), &mut secrets, &self.encryptor, req.dry_run).await {
+            Ok(report) => {
+                let message = if report.dry_run {
+                    format!("Dry run: {} secrets can be re-encrypted; nothing was changed", report.total())
+                } else {
+                    format!("Rotated master key and re-encrypted {} secrets", report.total())
+                };
+                Ok(Response::new(RotateEncryptionKeyResponse {
+                    success: true,
+                    message,
+                    key_source,
+                    total: report.total(),
+                    columns: report.columns.into_iter()
+                        .map(|(column, rows)| ReencryptedColumn { column, rows })
+                        .collect(),
+                }))
+            }
+            Err(e) => {
+                error!("Encryption key rotation failed: {}", e);
+                Err(Status::failed_precondition(format!("Key rotation failed: {}", e)))
+            }
+        }
+    }
+
+    async fn list_workspaces(&self, request: Request<ListWorkspacesRequest>) -> Result<Response<ListWorkspacesResponse>, Status> {
+        let allowed = Self::caller_workspace(&request);
+        let workspaces = self.workspaces.repo().list_workspaces().await
+            .map_err(|e| Status::internal(format!("Failed to list workspaces: {}", e)))?;
+
+        Ok(Response::new(ListWorkspacesResponse {
+            workspaces: workspaces.iter()
+                .filter(|ws| allowed.is_none_or(|id| id == ws.workspace_id))
+                .map(Self::workspace_to_proto)
+                .collect(),
+        }))
+    }
+
+    async fn create_workspace(&self, request: Request<CreateWorkspaceRequest>) -> Result<Response<CreateWorkspaceResponse>, Status> {
+        let req = request.into_inner();
+        let name = req.name.trim().to_lowercase();
+        if !ws_model::Workspace::is_valid_name(&name) {
+            return Err(Status::invalid_argument(format!(
+                "Invalid workspace name '{}': use lowercase letters, digits, '-' and '_'", req.name
+            )));
+        }
+
+        let repo = self.workspaces.repo();
+        if repo.get_workspace_by_name(&name).await
+            .map_err(|e| Status::internal(format!("Failed to look up workspace: {}", e)))?
+            .is_some()
+        {
+            return Err(Status::already_exists(format!("Workspace '{}' already exists", name)));
+        }
+
+        let ws = ws_model::Workspace {
+            workspace_id: uuid::Uuid::new_v4(),
+            name,
+            display_name: if req.display_name.is_empty() { None } else { Some(req.display_name) },
+            created_at: Utc::now(),
+        };
+        repo.create_workspace(&ws).await
+            .map_err(|e| Status::internal(format!("Failed to create workspace: {}", e)))?;
+        info!("Created workspace '{}' ({})", ws.name, ws.workspace_id);
+
+        Ok(Response::new(CreateWorkspaceResponse {
+            workspace: Some(Self::workspace_to_proto(&ws)),
+        }))
+    }
+
+    async fn delete_workspace(&self, request: Request<DeleteWorkspaceRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        let repo = self.workspaces.repo();
+        let ws = repo.get_workspace_by_name(req.name.trim()).await
+            .map_err(|e| Status::internal(format!("Failed to look up workspace: {}", e)))?
+            .ok_or_else(|| Status::not_found(format!("Workspace '{}' not found", req.name)))?;
+        if ws.is_default() {
+            return Err(Status::failed_precondition("The default workspace cannot be deleted"));
+        }
+
+        repo.delete_workspace(ws.workspace_id).await
+            .map_err(|e| match e {
+                maowbot_core::Error::ValidationError(msg) => Status::failed_precondition(msg),
+                other => Status::internal(format!("Failed to delete workspace: {}", other)),
+            })?;
+        if let Err(e) = self.workspace_router.reload().await {
+            error!("Failed to reload channel routes: {:?}", e);
+        }
+        warn!("Deleted workspace '{}' ({}) with its commands, redeems, config and channel routes", ws.name, ws.workspace_id);
+        Ok(Response::new(()))
+    }
+
+    async fn list_workspace_channels(&self, request: Request<ListWorkspaceChannelsRequest>) -> Result<Response<ListWorkspaceChannelsResponse>, Status> {
+        let allowed = Self::caller_workspace(&request);
+        let routes = self.workspace_router.routes().await
+            .map_err(|e| Status::internal(format!("Failed to list channel routes: {}", e)))?;
+        let names = self.workspace_names().await?;
+
+        let channels = routes.into_iter()
+            .filter(|r| allowed.is_none_or(|id| id == r.workspace_id))
+            .map(|r| WorkspaceChannel {
+                workspace: names.get(&r.workspace_id).cloned().unwrap_or_else(|| r.workspace_id.to_string()),
+                platform: r.platform,
+                channel: r.channel,
+                created_at: Some(prost_types::Timestamp {
+                    seconds: r.created_at.timestamp(),
+                    nanos: r.created_at.timestamp_subsec_nanos() as i32,
+                }),
+            })
+            .collect();
+        Ok(Response::new(ListWorkspaceChannelsResponse { channels }))
+    }
+
+    async fn assign_workspace_channel(&self, request: Request<AssignWorkspaceChannelRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        let ws = self.find_workspace(&req.workspace).await?;
+        self.workspace_router.assign(ws.workspace_id, &req.platform, &req.channel).await
+            .map_err(|e| match e {
+                maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+                other => Status::internal(format!("Failed to assign channel: {}", other)),
+            })?;
+        info!("Routed {} channel '{}' to workspace '{}'", req.platform, req.channel, ws.name);
+        Ok(Response::new(()))
+    }
+
+    async fn unassign_workspace_channel(&self, request: Request<UnassignWorkspaceChannelRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        let removed = self.workspace_router.unassign(&req.platform, &req.channel).await
+            .map_err(|e| Status::internal(format!("Failed to unassign channel: {}", e)))?;
+        if !removed {
+            return Err(Status::not_found(format!("{} channel '{}' is not routed to a workspace", req.platform, req.channel)));
+        }
+        info!("{} channel '{}' now uses the default workspace", req.platform, req.channel);
+        Ok(Response::new(()))
+    }
+
+    async fn list_api_tokens(&self, request: Request<ListApiTokensRequest>) -> Result<Response<ListApiTokensResponse>, Status> {
+        let req = request.into_inner();
+        let tokens = self.api_tokens.repo().list_tokens().await
+            .map_err(|e| Status::internal(format!("Failed to list API tokens: {}", e)))?;
+
+        let names = self.workspace_names().await?;
+        let tokens = tokens.iter()
+            .filter(|t| req.include_revoked || t.revoked_at.is_none())
+            .map(|t| Self::api_token_to_proto(t, &names))
+            .collect();
+        Ok(Response::new(ListApiTokensResponse { tokens }))
+    }
+
+    async fn create_api_token(&self, request: Request<CreateApiTokenRequest>) -> Result<Response<CreateApiTokenResponse>, Status> {
+        let req = request.into_inner();
+        let role: ApiRole = req.role.parse()
+            .map_err(|e: maowbot_core::Error| Status::invalid_argument(e.to_string()))?;
+
+        let workspace = if req.workspace.trim().is_empty() {
+            None
+        } else {
+            Some(self.find_workspace(&req.workspace).await?)
+        };
+
+        let (token, secret) = self.api_tokens.create(&req.name, role, workspace.as_ref().map(|ws| ws.workspace_id)).await
+            .map_err(|e| match e {
+                maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+                other => Status::internal(format!("Failed to create API token: {}", other)),
+            })?;
+        match &workspace {
+            Some(ws) => info!("Created API token '{}' with role {} for workspace '{}'", token.name, token.role, ws.name),
+            None => info!("Created API token '{}' with role {}", token.name, token.role),
+        }
+
+        let names = workspace.into_iter().map(|ws| (ws.workspace_id, ws.name)).collect();
+        Ok(Response::new(CreateApiTokenResponse {
+            token: Some(Self::api_token_to_proto(&token, &names)),
+            secret,
+        }))
+    }
+
+    async fn revoke_api_token(&self, request: Request<RevokeApiTokenRequest>) -> Result<Response<()>, Status> {
+        let caller = request.extensions().get::<Caller>().cloned();
+        let req = request.into_inner();
+        // Revoking the token in use would lock this client out mid-session
+        if caller.as_ref().is_some_and(|c| c.name == req.name.trim()) {
+            return Err(Status::failed_precondition("Cannot revoke the token this client is using"));
+        }
+        let token = self.api_tokens.revoke(req.name.trim()).await
+            .map_err(|e| match e {
+                maowbot_core::Error::NotFound(msg) => Status::not_found(msg),
+                other => Status::internal(format!("Failed to revoke API token: {}", other)),
+            })?;
+        match caller {
+            Some(c) => warn!("Revoked API token '{}' ({}); requested by '{}' ({})", token.name, token.role, c.name, c.role),
+            None => warn!("Revoked API token '{}' ({})", token.name, token.role),
+        }
+        Ok(Response::new(()))
+    }
+
+    async fn list_audit_log(&self, request: Request<ListAuditLogRequest>) -> Result<Response<ListAuditLogResponse>, Status> {
+        let req = request.into_inner();
+        let limit = if req.limit > 0 { req.limit.min(1000) } else { 50 };
+        let text = |s: String| (!s.trim().is_empty()).then(|| s.trim().to_string());
+        let filter = token_model::AuditLogFilter {
+            method: text(req.method),
+            client_name: text(req.client_name),
+            target: text(req.target),
+            since: req.since.and_then(|t| chrono::DateTime::from_timestamp(t.seconds, t.nanos.max(0) as u32)),
+            changes_only: req.changes_only,
+        };
+        let entries = self.api_tokens.repo().list_audit_entries(&filter, limit as i64).await
+            .map_err(|e| Status::internal(format!("Failed to read audit log: {}", e)))?;
+        let json = |v: Option<serde_json::Value>| v.map(|v| v.to_string()).unwrap_or_default();
+
+        let entries = entries.into_iter().map(|e| AuditLogEntry {
+            audit_id: e.audit_id,
+            client_name: e.client_name.unwrap_or_default(),
+            role: e.role.unwrap_or_default(),
+            method: e.method,
+            workspace: e.workspace.unwrap_or_default(),
+            allowed: e.allowed,
+            reason: e.reason.unwrap_or_default(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: e.created_at.timestamp(),
+                nanos: e.created_at.timestamp_subsec_nanos() as i32,
+            }),
+            target: e.target.unwrap_or_default(),
+            before_value: json(e.before_value),
+            after_value: json(e.after_value),
+            outcome: e.outcome.unwrap_or_default(),
+        }).collect();
+        Ok(Response::new(ListAuditLogResponse { entries }))
+    }
+
+    async fn export_backup(&self, _: Request<ExportBackupRequest>) -> Result<Response<ExportBackupResponse>, Status> {
+        let repos = self.backup_repos();
+        let (archive, backup_key, counts) = backup::export_backup(&repos, self.repos.pipelines.as_ref()).await
+            .map_err(|e| Status::internal(format!("Failed to export backup: {}", e)))?;
+        let data = archive.to_bytes()
+            .map_err(|e| Status::internal(format!("Failed to serialize backup: {}", e)))?;
+        info!("Exported full backup ({} bytes, schema version {})", data.len(), archive.schema_version);
+
+        Ok(Response::new(ExportBackupResponse {
+            archive: data,
+            backup_key,
+            schema_version: archive.schema_version,
+            counts: Some(Self::backup_counts_to_proto(&counts)),
+        }))
+    }
+
+    async fn import_backup(&self, request: Request<ImportBackupRequest>) -> Result<Response<ImportBackupResponse>, Status> {
+        let req = request.into_inner();
+        let archive = backup::BackupArchive::from_bytes(&req.archive)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let backup_key = Some(req.backup_key.trim()).filter(|k| !k.is_empty());
+
+        let repos = self.backup_repos();
+        let report = backup::import_backup(&repos, self.repos.pipelines.as_ref(), &archive, backup_key).await
+            .map_err(|e| match e {
+                maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+                other => Status::internal(format!("Failed to import backup: {}", other)),
+            })?;
+        warn!(
+            "Imported backup from {} (schema version {}) with {} warnings",
+            archive.created_at, archive.schema_version, report.warnings.len()
+        );
+
+        Ok(Response::new(ImportBackupResponse {
+            restored: Some(Self::backup_counts_to_proto(&report.restored)),
+            skipped: Some(Self::backup_counts_to_proto(&report.skipped)),
+            warnings: report.warnings,
+            schema_version: archive.schema_version,
+        }))
+    }
+
+    async fn get_log_levels(&self, _: Request<GetLogLevelsRequest>) -> Result<Response<GetLogLevelsResponse>, Status> {
+        let control = logging::log_control()
+            .ok_or_else(|| Status::unavailable("Logging is not initialized"))?;
+        let levels = control.levels();
+
+        Ok(Response::new(GetLogLevelsResponse {
+            format: control.format().as_str().to_string(),
+            base_filter: levels.base.clone(),
+            overrides: Self::log_overrides_to_proto(&levels),
+            effective_filter: levels.filter_string(),
+        }))
+    }
+
+    async fn set_log_level(&self, request: Request<SetLogLevelRequest>) -> Result<Response<SetLogLevelResponse>, Status> {
+        let caller = request.extensions().get::<Caller>()
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| "console".to_string());
+        let req = request.into_inner();
+        let control = logging::log_control()
+            .ok_or_else(|| Status::unavailable("Logging is not initialized"))?;
+
+        let module = req.module.trim();
+        let levels = if req.level.trim().is_empty() {
+            control.reset_module_level(module)
+        } else {
+            control.set_module_level(module, req.level.trim())
+        }.map_err(Status::invalid_argument)?;
+
+        warn!("Log filter changed by '{}' => {}", caller, levels.filter_string());
+        Ok(Response::new(SetLogLevelResponse {
+            effective_filter: levels.filter_string(),
+            overrides: Self::log_overrides_to_proto(&levels),
+        }))
+    }
+
+    async fn get_settings_schema(&self, request: Request<GetSettingsSchemaRequest>) -> Result<Response<GetSettingsSchemaResponse>, Status> {
+        let req = request.into_inner();
+        let category = req.category.trim();
+
+        let settings = SettingsRegistry::definitions().iter()
+            .filter(|def| category.is_empty() || def.category.eq_ignore_ascii_case(category))
+            .map(|def| {
+                let current_value = if def.is_secret && self.settings.is_set(def.key) {
+                    "<redacted>".to_string()
+                } else {
+                    self.settings.get(def.key).unwrap_or_default()
+                };
+                SettingSchema {
+                    key: def.key.to_string(),
+                    metadata: Some(Self::definition_to_metadata(def)),
+                    requires_restart: def.requires_restart,
+                    min: def.min,
+                    max: def.max,
+                    current_value,
+                    is_set: self.settings.is_set(def.key),
+                }
+            })
+            .collect();
+
+        Ok(Response::new(GetSettingsSchemaResponse { settings }))
+    }
+
+    async fn get_update_status(&self, _: Request<GetUpdateStatusRequest>) -> Result<Response<UpdateStatus>, Status> {
+        Ok(Response::new(self.update_status_to_proto()))
+    }
+
+    async fn check_for_update(&self, _: Request<CheckForUpdateRequest>) -> Result<Response<UpdateStatus>, Status> {
+        self.updater.check().await.map_err(Self::update_error_to_status)?;
+        Ok(Response::new(self.update_status_to_proto()))
+    }
+
+    async fn apply_update(&self, request: Request<ApplyUpdateRequest>) -> Result<Response<ApplyUpdateResponse>, Status> {
+        let req = request.into_inner();
+        let delay = if req.restart_delay_seconds > 0 { req.restart_delay_seconds } else { 10 };
+
+        let Some(version) = self.updater.download_and_install().await.map_err(Self::update_error_to_status)? else {
+            return Ok(Response::new(ApplyUpdateResponse {
+                installed: false,
+                version: self.updater.current_version(),
+                message: "Already running the latest version".to_string(),
+                restart_at: None,
+            }));
+        };
+
+        info!("Update to {} installed; restarting in {}s", version, delay);
+        let restart_at = Utc::now() + chrono::Duration::seconds(delay as i64);
+        self.updater.schedule_restart(std::time::Duration::from_secs(delay as u64));
+
+        Ok(Response::new(ApplyUpdateResponse {
+            installed: true,
+            message: format!("Updated to {}; restarting in {} seconds", version, delay),
+            version,
+            restart_at: Some(prost_types::Timestamp {
+                seconds: restart_at.timestamp(),
+                nanos: restart_at.timestamp_subsec_nanos() as i32,
+            }),
+        }))
+    }
+
+    async fn get_database_status(&self, _: Request<GetDatabaseStatusRequest>) -> Result<Response<GetDatabaseStatusResponse>, Status> {
+        let snapshots = DbMaintenance::list_snapshots()
+            .map_err(|e| Status::internal(format!("Failed to list snapshots: {}", e)))?;
+        Ok(Response::new(GetDatabaseStatusResponse {
+            server_version: self.db_maintenance.server_version().await.unwrap_or_default(),
+            embedded: DbMaintenance::embedded(),
+            database_bytes: self.db_maintenance.database_size().await
+                .map_err(|e| Status::internal(e.to_string()))?,
+            wal_bytes: crate::portable_postgres::wal_size(crate::portable_postgres::PG_DATA_DIR) as i64,
+            snapshots: snapshots.iter().map(Self::snapshot_to_proto).collect(),
+            integrity: self.db_maintenance.last_integrity().as_ref().map(Self::integrity_to_proto),
+            last_compacted_at: self.db_maintenance.last_compacted().map(Self::timestamp),
+        }))
+    }
+
+    async fn create_database_snapshot(&self, request: Request<CreateDatabaseSnapshotRequest>) -> Result<Response<DatabaseSnapshot>, Status> {
+        let req = request.into_inner();
+        let label = Some(req.label.trim()).filter(|l| !l.is_empty());
+        let snapshot = self.db_maintenance.create_snapshot(label).await
+            .map_err(|e| Status::internal(format!("Snapshot failed: {}", e)))?;
+        info!("Database snapshot {} created ({} bytes)", snapshot.name, snapshot.size_bytes);
+        Ok(Response::new(Self::snapshot_to_proto(&snapshot)))
+    }
+
+    async fn compact_database(&self, request: Request<CompactDatabaseRequest>) -> Result<Response<CompactDatabaseResponse>, Status> {
+        let req = request.into_inner();
+        let report = self.db_maintenance.compact(req.full).await
+            .map_err(|e| Status::internal(format!("Compaction failed: {}", e)))?;
+        Ok(Response::new(CompactDatabaseResponse {
+            database_bytes_before: report.database_bytes_before,
+            database_bytes_after: report.database_bytes_after,
+            wal_bytes_before: report.wal_bytes_before as i64,
+            wal_bytes_after: report.wal_bytes_after as i64,
+            log_rotated: report.log_rotated,
+            duration_ms: report.duration.as_millis() as i64,
+            warnings: report.warnings,
+        }))
+    }
+
+    async fn check_database_integrity(&self, _: Request<CheckDatabaseIntegrityRequest>) -> Result<Response<DatabaseIntegrityReport>, Status> {
+        let report = self.db_maintenance.check_integrity().await;
+        Ok(Response::new(Self::integrity_to_proto(&report)))
+    }
+
+    async fn run_retention(&self, request: Request<RunRetentionRequest>) -> Result<Response<RetentionReport>, Status> {
+        let req = request.into_inner();
+        let report = self.retention.run(req.dry_run).await
+            .map_err(|e| Status::internal(format!("Retention failed: {}", e)))?;
+        Ok(Response::new(Self::retention_to_proto(&report)))
+    }
+
+    async fn get_certificate_info(&self, _: Request<GetCertificateInfoRequest>) -> Result<Response<CertificateInfo>, Status> {
+        Ok(Response::new(self.certificate_info_to_proto()))
+    }
+
+    async fn regenerate_certificate(&self, request: Request<RegenerateCertificateRequest>) -> Result<Response<CertificateInfo>, Status> {
+        let req = request.into_inner();
+        if !req.extra_sans.is_empty() {
+            self.settings.set("tls.extra_sans", &req.extra_sans.join(",")).await
+                .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        }
+        let sans = tls::server_sans(&self.settings);
+        tls::generate_server_cert(sans.clone())
+            .map_err(|e| Status::internal(format!("Certificate generation failed: {}", e)))?;
+        info!("Server certificate regenerated for {:?}; takes effect after a restart", sans);
+        Ok(Response::new(self.certificate_info_to_proto()))
+    }
+
+    async fn issue_client_certificate(&self, request: Request<IssueClientCertificateRequest>) -> Result<Response<IssueClientCertificateResponse>, Status> {
+        let req = request.into_inner();
+        let name = req.name.trim();
+        let issued = tls::issue_client_cert(name).map_err(|e| match e {
+            maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+            maowbot_core::Error::NotFound(msg) => Status::failed_precondition(msg),
+            other => Status::internal(other.to_string()),
+        })?;
+        info!("Issued client certificate '{}' ({})", name, issued.fingerprint);
+        Ok(Response::new(IssueClientCertificateResponse {
+            cert_pem: issued.cert_pem,
+            key_pem: issued.key_pem,
+            ca_pem: issued.ca_pem,
+            fingerprint: issued.fingerprint,
+        }))
+    }
+}
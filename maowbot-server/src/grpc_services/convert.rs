@@ -0,0 +1,51 @@
+// Conversions shared by the gRPC services: timestamps to and from protobuf,
+// and core errors to a `Status` with the matching code.
+
+use chrono::{DateTime, TimeZone, Utc};
+use tonic::Status;
+use maowbot_core::Error;
+
+pub fn to_timestamp(t: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: t.timestamp(),
+        nanos: t.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// `None` for a timestamp chrono can't represent.
+pub fn from_timestamp(ts: &prost_types::Timestamp) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(ts.seconds, ts.nanos.max(0) as u32).single()
+}
+
+/// Missing things are NotFound, bad input is InvalidArgument, a platform that
+/// can't do it right now is FailedPrecondition and the rest is Internal.
+pub fn to_status(e: Error) -> Status {
+    match e {
+        Error::NotFound(msg) => Status::not_found(msg),
+        Error::Parse(msg) | Error::ValidationError(msg) => Status::invalid_argument(msg),
+        Error::Platform(msg) => Status::failed_precondition(msg),
+        other => Status::internal(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        let t = Utc.timestamp_opt(1_700_000_000, 123_000_000).unwrap();
+        assert_eq!(from_timestamp(&to_timestamp(t)), Some(t));
+        assert_eq!(from_timestamp(&prost_types::Timestamp { seconds: i64::MAX, nanos: 0 }), None);
+    }
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(to_status(Error::NotFound("x".into())).code(), Code::NotFound);
+        assert_eq!(to_status(Error::Parse("x".into())).code(), Code::InvalidArgument);
+        assert_eq!(to_status(Error::ValidationError("x".into())).code(), Code::InvalidArgument);
+        assert_eq!(to_status(Error::Platform("x".into())).code(), Code::FailedPrecondition);
+        assert_eq!(to_status(Error::Internal("x".into())).code(), Code::Internal);
+    }
+}
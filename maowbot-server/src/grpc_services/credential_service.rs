@@ -10,37 +10,65 @@ use maowbot_proto::maowbot::{
 };
 use maowbot_core::{
     auth::manager::AuthManager,
-    repositories::postgres::credentials::PostgresCredentialsRepository,
-    repositories::postgres::user::UserRepository,
+    repositories::Repositories,
+    services::twitch::scope_check::{stored_scopes, ScopeCheckService, ScopeReport},
+    services::chat_identity::ChatIdentityRouter,
 };
 use tokio::sync::Mutex;
+use maowbot_common::models::auth::AuthenticationPrompt;
 use maowbot_common::models::platform::PlatformCredential as Credential;
-use maowbot_common::traits::repository_traits::{CredentialsRepository, UserRepo};
+use maowbot_common::traits::repository_traits::CredentialsRepository;
 use std::sync::Arc;
 use std::str::FromStr;
 use std::collections::HashMap;
 use chrono::Utc;
 use uuid::Uuid;
 use tracing::{info, debug};
+use crate::authz::AuditNote;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use super::workspace::WorkspaceResolver;
 
 pub struct CredentialServiceImpl {
     auth_manager: Arc<Mutex<AuthManager>>,
-    credential_repo: Arc<PostgresCredentialsRepository>,
-    user_repo: Arc<UserRepository>,
+    repos: Repositories,
+    workspaces: WorkspaceResolver,
+    scope_check: Arc<ScopeCheckService>,
+    chat_identities: Arc<ChatIdentityRouter>,
 }
 
 impl CredentialServiceImpl {
     pub fn new(
         auth_manager: Arc<Mutex<AuthManager>>,
-        credential_repo: Arc<PostgresCredentialsRepository>,
-        user_repo: Arc<UserRepository>,
+        repos: Repositories,
+        workspaces: WorkspaceResolver,
+        scope_check: Arc<ScopeCheckService>,
+        chat_identities: Arc<ChatIdentityRouter>,
     ) -> Self {
         Self {
             auth_manager,
-            credential_repo,
-            user_repo,
+            repos,
+            workspaces,
+            scope_check,
+            chat_identities,
         }
     }
+
+    /// The credentials repository for the workspace selected in `request`'s metadata.
+    async fn credential_repo_for<T>(&self, request: &Request<T>) -> Result<Arc<dyn CredentialsRepository + Send + Sync>, Status> {
+        Ok(self.repos.for_workspace(self.workspaces.resolve(request).await?).credentials)
+    }
+
+    /// The auth manager stores new credentials in the default workspace; move them
+    /// to the workspace the request was made in.
+    async fn claim_for_workspace(&self, credential: &Credential, workspace_id: Uuid) -> Result<(), Status> {
+        if workspace_id == DEFAULT_WORKSPACE_ID {
+            return Ok(());
+        }
+        self.repos.credentials
+            .assign_workspace(credential.credential_id, workspace_id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to assign credential to workspace: {}", e)))
+    }
     
     fn credential_to_proto(cred: &Credential) -> PlatformCredential {
         PlatformCredential {
@@ -50,6 +78,7 @@ impl CredentialServiceImpl {
                 maowbot_common::models::platform::Platform::TwitchEventSub => Platform::TwitchEventsub as i32,
                 maowbot_common::models::platform::Platform::Discord => Platform::Discord as i32,
                 maowbot_common::models::platform::Platform::VRChat => Platform::Vrchat as i32,
+                maowbot_common::models::platform::Platform::Kick => Platform::Kick as i32,
                 maowbot_common::models::platform::Platform::Twitch => Platform::TwitchHelix as i32,
                 _ => Platform::Unknown as i32,
             },
@@ -62,7 +91,7 @@ impl CredentialServiceImpl {
                 seconds: ts.timestamp(),
                 nanos: ts.timestamp_subsec_nanos() as i32,
             }),
-            scopes: vec![], // We don't have scopes in the model
+            scopes: stored_scopes(cred),
             created_at: Some(prost_types::Timestamp {
                 seconds: cred.created_at.timestamp(),
                 nanos: cred.created_at.timestamp_subsec_nanos() as i32,
@@ -78,6 +107,24 @@ impl CredentialServiceImpl {
         }
     }
     
+    fn scope_report_to_proto(report: &ScopeReport) -> CredentialScopeStatus {
+        CredentialScopeStatus {
+            credential_id: report.credential_id.to_string(),
+            platform: match report.platform {
+                maowbot_common::models::platform::Platform::TwitchIRC => Platform::TwitchIrc as i32,
+                maowbot_common::models::platform::Platform::TwitchEventSub => Platform::TwitchEventsub as i32,
+                _ => Platform::TwitchHelix as i32,
+            },
+            user_name: report.user_name.clone(),
+            is_broadcaster: report.is_broadcaster,
+            missing_scopes: report.missing.clone(),
+            affected_features: report.affected.iter().map(|f| f.to_string()).collect(),
+            verified: report.verified,
+            error_message: report.error.clone().unwrap_or_default(),
+            reauth_command: report.reauth_command().unwrap_or_default(),
+        }
+    }
+
     fn user_to_proto(user: &maowbot_common::models::user::User) -> User {
         User {
             user_id: user.user_id.to_string(),
@@ -95,6 +142,33 @@ impl CredentialServiceImpl {
     }
 }
 
+/// Expiry fields of a batch validation entry: seconds left (0 without an
+/// expiry), whether it has expired, and whether it expires within `within`.
+fn expiry_status(
+    expires_at: Option<chrono::DateTime<Utc>>,
+    now: chrono::DateTime<Utc>,
+    within: chrono::Duration,
+) -> (i64, bool, bool) {
+    let seconds_until_expiry = expires_at.map(|exp| (exp - now).num_seconds()).unwrap_or(0);
+    let expired = expires_at.map(|exp| exp <= now).unwrap_or(false);
+    let expiring_soon = !expired && expires_at.map(|exp| exp <= now + within).unwrap_or(false);
+    (seconds_until_expiry, expired, expiring_soon)
+}
+
+/// (valid, checked, error) for a credential: the platform's answer when it
+/// gave one, otherwise whether the credential has expired.
+fn validation_outcome(
+    check: Option<Result<bool, maowbot_core::Error>>,
+    expired: bool,
+) -> (bool, bool, String) {
+    match check {
+        None => (!expired, false, String::new()),
+        Some(Ok(true)) => (true, true, String::new()),
+        Some(Ok(false)) => (false, true, "Rejected by platform".to_string()),
+        Some(Err(e)) => (!expired, false, format!("Validation failed: {}", e)),
+    }
+}
+
 #[tonic::async_trait]
 impl CredentialService for CredentialServiceImpl {
     async fn begin_auth_flow(
@@ -113,17 +187,46 @@ impl CredentialService for CredentialServiceImpl {
             Platform::TwitchEventsub => "twitch-eventsub",
             Platform::Discord => "discord",
             Platform::Vrchat => "vrchat",
+            Platform::Kick => "kick",
             Platform::TwitchHelix => "twitch-helix",
             _ => return Err(Status::invalid_argument("Unsupported platform")),
         };
         
+        let platform_internal = maowbot_common::models::platform::Platform::from_str(platform_str).unwrap();
+        
+        if req.device_code {
+            let prompt = self.auth_manager
+                .lock()
+                .await
+                .begin_device_auth_flow(platform_internal, req.is_bot)
+                .await
+                .map_err(|e| Status::failed_precondition(format!("Failed to begin device login: {}", e)))?;
+            
+            let AuthenticationPrompt::DeviceCode { user_code, verification_uri, expires_in, interval } = prompt else {
+                return Err(Status::internal("Authenticator did not return a device code"));
+            };
+            let expires_at = Utc::now() + chrono::Duration::seconds(expires_in as i64);
+            let mut metadata = HashMap::new();
+            metadata.insert("flow".to_string(), "device_code".to_string());
+            metadata.insert("user_code".to_string(), user_code);
+            metadata.insert("interval".to_string(), interval.to_string());
+            
+            return Ok(Response::new(BeginAuthFlowResponse {
+                auth_url: verification_uri,
+                state: String::new(),
+                code_verifier: String::new(),
+                expires_at: Some(prost_types::Timestamp {
+                    seconds: expires_at.timestamp(),
+                    nanos: 0,
+                }),
+                metadata,
+            }));
+        }
+        
         let auth_url = self.auth_manager
             .lock()
             .await
-            .begin_auth_flow(
-                maowbot_common::models::platform::Platform::from_str(platform_str).unwrap(),
-                req.is_bot,
-            )
+            .begin_auth_flow(platform_internal, req.is_bot)
             .await
             .map_err(|e| Status::internal(format!("Failed to begin auth flow: {}", e)))?;
         
@@ -140,6 +243,7 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<CompleteAuthFlowRequest>,
     ) -> Result<Response<CompleteAuthFlowResponse>, Status> {
+        let workspace_id = self.workspaces.resolve(&request).await?;
         let req = request.into_inner();
         let platform = Platform::try_from(req.platform)
             .map_err(|_| Status::invalid_argument("Invalid platform"))?;
@@ -151,6 +255,7 @@ impl CredentialService for CredentialServiceImpl {
             Platform::TwitchEventsub => "twitch-eventsub",
             Platform::Discord => "discord",
             Platform::Vrchat => "vrchat",
+            Platform::Kick => "kick",
             Platform::TwitchHelix => "twitch-helix",
             _ => return Err(Status::invalid_argument("Unsupported platform")),
         };
@@ -174,11 +279,13 @@ impl CredentialService for CredentialServiceImpl {
                     )
                     .await
                     .map_err(|e| Status::internal(format!("Failed to complete auth flow: {}", e)))?;
+                self.claim_for_workspace(&credential, workspace_id).await?;
                 
                 Ok(Response::new(CompleteAuthFlowResponse {
                     credential: Some(Self::credential_to_proto(&credential)),
                     requires_2fa: false,
                     session_token: String::new(),
+                    pending: false,
                 }))
             }
             Some(complete_auth_flow_request::AuthData::CredentialsMap(creds_data)) => {
@@ -207,11 +314,13 @@ impl CredentialService for CredentialServiceImpl {
                             Status::internal(format!("Failed to complete auth flow: {}", e))
                         }
                     })?;
+                self.claim_for_workspace(&credential, workspace_id).await?;
                 
                 Ok(Response::new(CompleteAuthFlowResponse {
                     credential: Some(Self::credential_to_proto(&credential)),
                     requires_2fa: false,
                     session_token: String::new(),
+                    pending: false,
                 }))
             }
             Some(complete_auth_flow_request::AuthData::TwoFactorCode(twofa_data)) => {
@@ -228,11 +337,34 @@ impl CredentialService for CredentialServiceImpl {
                     )
                     .await
                     .map_err(|e| Status::internal(format!("Failed to complete 2FA: {}", e)))?;
+                self.claim_for_workspace(&credential, workspace_id).await?;
                 
                 Ok(Response::new(CompleteAuthFlowResponse {
                     credential: Some(Self::credential_to_proto(&credential)),
                     requires_2fa: false,
                     session_token: String::new(),
+                    pending: false,
+                }))
+            }
+            Some(complete_auth_flow_request::AuthData::DeviceCode(device_data)) => {
+                let user_id = Uuid::parse_str(&device_data.user_id)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid user_id: {}", e)))?;
+                
+                let credential = self.auth_manager
+                    .lock()
+                    .await
+                    .poll_device_auth_flow(platform_internal, &user_id)
+                    .await
+                    .map_err(|e| Status::failed_precondition(format!("Device login failed: {}", e)))?;
+                if let Some(credential) = &credential {
+                    self.claim_for_workspace(credential, workspace_id).await?;
+                }
+                
+                Ok(Response::new(CompleteAuthFlowResponse {
+                    pending: credential.is_none(),
+                    credential: credential.as_ref().map(Self::credential_to_proto),
+                    requires_2fa: false,
+                    session_token: String::new(),
                 }))
             }
             None => Err(Status::invalid_argument("Missing auth data")),
@@ -243,17 +375,18 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<ListCredentialsRequest>,
     ) -> Result<Response<ListCredentialsResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Listing credentials");
         
         let credentials = if req.platforms.is_empty() {
-            self.credential_repo
+            credential_repo
                 .get_all_credentials()
                 .await
                 .map_err(|e| Status::internal(format!("Failed to list credentials: {}", e)))?
         } else {
             // Get all credentials and filter by requested platforms
-            let all_creds = self.credential_repo
+            let all_creds = credential_repo
                 .get_all_credentials()
                 .await
                 .map_err(|e| Status::internal(format!("Failed to list credentials: {}", e)))?;
@@ -266,6 +399,7 @@ impl CredentialService for CredentialServiceImpl {
                         Ok(Platform::TwitchEventsub) => Some(maowbot_common::models::platform::Platform::TwitchEventSub),
                         Ok(Platform::Discord) => Some(maowbot_common::models::platform::Platform::Discord),
                         Ok(Platform::Vrchat) => Some(maowbot_common::models::platform::Platform::VRChat),
+                        Ok(Platform::Kick) => Some(maowbot_common::models::platform::Platform::Kick),
                         Ok(Platform::TwitchHelix) => Some(maowbot_common::models::platform::Platform::Twitch),
                         _ => None,
                     }
@@ -288,7 +422,7 @@ impl CredentialService for CredentialServiceImpl {
             };
             
             // Look up the user for this credential
-            let user_proto = match self.user_repo.get(cred.user_id).await {
+            let user_proto = match self.repos.users.get(cred.user_id).await {
                 Ok(Some(user)) => Some(Self::user_to_proto(&user)),
                 Ok(None) => {
                     debug!("No user found for credential user_id: {}", cred.user_id);
@@ -317,6 +451,7 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<RefreshCredentialRequest>,
     ) -> Result<Response<RefreshCredentialResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         
         let credential_id = match req.identifier {
@@ -330,7 +465,7 @@ impl CredentialService for CredentialServiceImpl {
         info!("Refreshing credential: {}", credential_id);
         
         // Get the credential
-        let credential = self.credential_repo
+        let credential = credential_repo
             .get_credential_by_id(credential_id)
             .await
             .map_err(|e| Status::internal(format!("Failed to get credential: {}", e)))?
@@ -361,13 +496,14 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<GetCredentialRequest>,
     ) -> Result<Response<GetCredentialResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Getting credential by ID: {}", req.credential_id);
         
         let credential_id = Uuid::parse_str(&req.credential_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid credential_id: {}", e)))?;
         
-        let credential = self.credential_repo
+        let credential = credential_repo
             .get_credential_by_id(credential_id)
             .await
             .map_err(|e| Status::internal(format!("Failed to get credential: {}", e)))?
@@ -392,6 +528,7 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<StoreCredentialRequest>,
     ) -> Result<Response<StoreCredentialResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         let cred_proto = req.credential.ok_or_else(|| Status::invalid_argument("Missing credential"))?;
         
@@ -405,6 +542,7 @@ impl CredentialService for CredentialServiceImpl {
             Platform::TwitchEventsub => maowbot_common::models::platform::Platform::TwitchEventSub,
             Platform::Discord => maowbot_common::models::platform::Platform::Discord,
             Platform::Vrchat => maowbot_common::models::platform::Platform::VRChat,
+            Platform::Kick => maowbot_common::models::platform::Platform::Kick,
             Platform::TwitchHelix => maowbot_common::models::platform::Platform::Twitch,
             _ => return Err(Status::invalid_argument("Unsupported platform")),
         };
@@ -421,7 +559,7 @@ impl CredentialService for CredentialServiceImpl {
         
         // Get the existing credential if updating
         let existing = if req.update_if_exists {
-            self.credential_repo
+            credential_repo
                 .get_credential_by_id(credential_id)
                 .await
                 .map_err(|e| Status::internal(format!("Failed to get existing credential: {}", e)))?
@@ -436,7 +574,7 @@ impl CredentialService for CredentialServiceImpl {
             existing_cred.is_teammate = cred_proto.is_teammate;
             existing_cred.updated_at = Utc::now();
             
-            self.credential_repo
+            credential_repo
                 .store_credentials(&existing_cred)
                 .await
                 .map_err(|e| Status::internal(format!("Failed to update credential: {}", e)))?;
@@ -449,7 +587,7 @@ impl CredentialService for CredentialServiceImpl {
             // Special case: EventSub credentials can be created by copying from Helix
             if platform_internal == maowbot_common::models::platform::Platform::TwitchEventSub {
                 // Look for existing Helix credential for this user
-                let helix_cred = self.credential_repo
+                let helix_cred = credential_repo
                     .get_credentials(&maowbot_common::models::platform::Platform::Twitch, user_id)
                     .await
                     .map_err(|e| Status::internal(format!("Failed to get Helix credential: {}", e)))?;
@@ -474,7 +612,7 @@ impl CredentialService for CredentialServiceImpl {
                         is_teammate: cred_proto.is_teammate,
                     };
                     
-                    self.credential_repo
+                    credential_repo
                         .store_credentials(&eventsub_cred)
                         .await
                         .map_err(|e| Status::internal(format!("Failed to create EventSub credential: {}", e)))?;
@@ -497,29 +635,41 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<RevokeCredentialRequest>,
     ) -> Result<Response<()>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Revoking credential");
         
-        let (platform, user_id) = match req.identifier {
+        let credential = match req.identifier {
             Some(revoke_credential_request::Identifier::CredentialId(id)) => {
                 let credential_id = Uuid::parse_str(&id)
                     .map_err(|e| Status::invalid_argument(format!("Invalid credential_id: {}", e)))?;
                 
-                let credential = self.credential_repo
+                credential_repo
                     .get_credential_by_id(credential_id)
                     .await
                     .map_err(|e| Status::internal(format!("Failed to get credential: {}", e)))?
-                    .ok_or_else(|| Status::not_found("Credential not found"))?;
-                
-                (credential.platform, credential.user_id)
+                    .ok_or_else(|| Status::not_found("Credential not found"))?
             }
             _ => return Err(Status::unimplemented("Platform user identifier not yet supported")),
         };
         
-        self.credential_repo
-            .delete_credentials(&platform, user_id)
+        credential_repo
+            .delete_credentials(&credential.platform, credential.user_id)
             .await
             .map_err(|e| Status::internal(format!("Failed to revoke credential: {}", e)))?;
+        // Never the tokens themselves
+        audit.change(
+            format!("credential:{}/{}", credential.platform, credential.user_name),
+            Some(serde_json::json!({
+                "credential_id": credential.credential_id,
+                "user_id": credential.user_id,
+                "platform_id": credential.platform_id,
+                "is_bot": credential.is_bot,
+                "expires_at": credential.expires_at,
+            })),
+            None,
+        );
         
         Ok(Response::new(()))
     }
@@ -528,6 +678,7 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<BatchRefreshCredentialsRequest>,
     ) -> Result<Response<BatchRefreshCredentialsResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         info!("Batch refreshing {} credentials", req.credential_ids.len());
         
@@ -536,7 +687,7 @@ impl CredentialService for CredentialServiceImpl {
         for credential_id_str in &req.credential_ids {
             let result = match Uuid::parse_str(credential_id_str) {
                 Ok(credential_id) => {
-                    match self.credential_repo.get_credential_by_id(credential_id).await {
+                    match credential_repo.get_credential_by_id(credential_id).await {
                         Ok(Some(credential)) => {
                             let mut auth_guard = self.auth_manager.lock().await;
                             match auth_guard.refresh_platform_credentials(&credential.platform, &credential.user_id).await {
@@ -593,6 +744,7 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<BatchListCredentialsRequest>,
     ) -> Result<Response<BatchListCredentialsResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Batch listing credentials");
         
@@ -602,6 +754,7 @@ impl CredentialService for CredentialServiceImpl {
                 maowbot_common::models::platform::Platform::TwitchEventSub,
                 maowbot_common::models::platform::Platform::Discord,
                 maowbot_common::models::platform::Platform::VRChat,
+                maowbot_common::models::platform::Platform::Kick,
             ]
         } else {
             req.platforms.iter()
@@ -611,6 +764,7 @@ impl CredentialService for CredentialServiceImpl {
                         Ok(Platform::TwitchEventsub) => Some(maowbot_common::models::platform::Platform::TwitchEventSub),
                         Ok(Platform::Discord) => Some(maowbot_common::models::platform::Platform::Discord),
                         Ok(Platform::Vrchat) => Some(maowbot_common::models::platform::Platform::VRChat),
+                        Ok(Platform::Kick) => Some(maowbot_common::models::platform::Platform::Kick),
                         _ => None,
                     }
                 })
@@ -621,7 +775,7 @@ impl CredentialService for CredentialServiceImpl {
         let mut by_platform = std::collections::HashMap::new();
         
         for platform in platforms {
-            let creds = self.credential_repo
+            let creds = credential_repo
                 .list_credentials_for_platform(&platform)
                 .await
                 .map_err(|e| Status::internal(format!("Failed to list credentials: {}", e)))?;
@@ -655,6 +809,7 @@ impl CredentialService for CredentialServiceImpl {
                     maowbot_common::models::platform::Platform::TwitchEventSub => Platform::TwitchEventsub,
                     maowbot_common::models::platform::Platform::Discord => Platform::Discord,
                     maowbot_common::models::platform::Platform::VRChat => Platform::Vrchat,
+                    maowbot_common::models::platform::Platform::Kick => Platform::Kick,
                     _ => Platform::Unknown,
                 };
                 
@@ -680,6 +835,7 @@ impl CredentialService for CredentialServiceImpl {
         &self,
         request: Request<GetCredentialHealthRequest>,
     ) -> Result<Response<GetCredentialHealthResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Getting credential health");
         
@@ -689,6 +845,7 @@ impl CredentialService for CredentialServiceImpl {
                 maowbot_common::models::platform::Platform::TwitchEventSub,
                 maowbot_common::models::platform::Platform::Discord,
                 maowbot_common::models::platform::Platform::VRChat,
+                maowbot_common::models::platform::Platform::Kick,
             ]
         } else {
             req.platforms.iter()
@@ -698,6 +855,7 @@ impl CredentialService for CredentialServiceImpl {
                         Ok(Platform::TwitchEventsub) => Some(maowbot_common::models::platform::Platform::TwitchEventSub),
                         Ok(Platform::Discord) => Some(maowbot_common::models::platform::Platform::Discord),
                         Ok(Platform::Vrchat) => Some(maowbot_common::models::platform::Platform::VRChat),
+                        Ok(Platform::Kick) => Some(maowbot_common::models::platform::Platform::Kick),
                         _ => None,
                     }
                 })
@@ -710,7 +868,7 @@ impl CredentialService for CredentialServiceImpl {
         let mut healthy_platforms = 0;
         
         for platform in platforms {
-            let creds = self.credential_repo
+            let creds = credential_repo
                 .list_credentials_for_platform(&platform)
                 .await
                 .map_err(|e| Status::internal(format!("Failed to list credentials: {}", e)))?;
@@ -757,6 +915,7 @@ impl CredentialService for CredentialServiceImpl {
                 maowbot_common::models::platform::Platform::TwitchEventSub => Platform::TwitchEventsub,
                 maowbot_common::models::platform::Platform::Discord => Platform::Discord,
                 maowbot_common::models::platform::Platform::VRChat => Platform::Vrchat,
+                maowbot_common::models::platform::Platform::Kick => Platform::Kick,
                 _ => Platform::Unknown,
             };
             
@@ -794,6 +953,93 @@ impl CredentialService for CredentialServiceImpl {
         }))
     }
     
+    async fn batch_validate_credentials(
+        &self,
+        request: Request<BatchValidateCredentialsRequest>,
+    ) -> Result<Response<BatchValidateCredentialsResponse>, Status> {
+        let credential_repo = self.credential_repo_for(&request).await?;
+        let req = request.into_inner();
+        debug!("Batch validating credentials");
+        
+        let platforms = if req.platforms.is_empty() {
+            vec![
+                maowbot_common::models::platform::Platform::Twitch,
+                maowbot_common::models::platform::Platform::TwitchIRC,
+                maowbot_common::models::platform::Platform::TwitchEventSub,
+                maowbot_common::models::platform::Platform::Discord,
+                maowbot_common::models::platform::Platform::VRChat,
+                maowbot_common::models::platform::Platform::Kick,
+            ]
+        } else {
+            req.platforms.iter()
+                .filter_map(|&p| {
+                    match Platform::try_from(p) {
+                        Ok(Platform::TwitchHelix) => Some(maowbot_common::models::platform::Platform::Twitch),
+                        Ok(Platform::TwitchIrc) => Some(maowbot_common::models::platform::Platform::TwitchIRC),
+                        Ok(Platform::TwitchEventsub) => Some(maowbot_common::models::platform::Platform::TwitchEventSub),
+                        Ok(Platform::Discord) => Some(maowbot_common::models::platform::Platform::Discord),
+                        Ok(Platform::Vrchat) => Some(maowbot_common::models::platform::Platform::VRChat),
+                        Ok(Platform::Kick) => Some(maowbot_common::models::platform::Platform::Kick),
+                        _ => None,
+                    }
+                })
+                .collect()
+        };
+        
+        let expiring_within = chrono::Duration::hours(if req.expiring_within_hours > 0 {
+            req.expiring_within_hours as i64
+        } else {
+            72
+        });
+        
+        let mut validations = Vec::new();
+        
+        for platform in platforms {
+            let creds = credential_repo
+                .list_credentials_for_platform(&platform)
+                .await
+                .map_err(|e| Status::internal(format!("Failed to list credentials: {}", e)))?;
+            
+            for cred in creds {
+                let (seconds_until_expiry, expired, expiring_soon) =
+                    expiry_status(cred.expires_at, Utc::now(), expiring_within);
+                
+                let check = if req.skip_platform_check {
+                    None
+                } else {
+                    let mut auth_guard = self.auth_manager.lock().await;
+                    Some(auth_guard.validate_credentials(&cred).await)
+                };
+                let (valid, checked, error_message) = validation_outcome(check, expired);
+                
+                validations.push(CredentialValidation {
+                    credential: Some(Self::credential_to_proto(&cred)),
+                    valid,
+                    checked,
+                    error_message,
+                    seconds_until_expiry,
+                    expiring_soon,
+                });
+            }
+        }
+        
+        let valid_count = validations.iter().filter(|v| v.valid).count() as i32;
+        let invalid_count = validations.len() as i32 - valid_count;
+        let expiring_count = validations.iter().filter(|v| v.expiring_soon).count() as i32;
+        
+        info!(
+            "Validated {} credentials: {} valid, {} invalid, {} expiring soon",
+            validations.len(), valid_count, invalid_count, expiring_count
+        );
+        
+        Ok(Response::new(BatchValidateCredentialsResponse {
+            validations,
+            valid_count,
+            invalid_count,
+            expiring_count,
+        }))
+    }
+    
     type StreamCredentialUpdatesStream = tonic::codec::Streaming<CredentialUpdateEvent>;
     
     async fn stream_credential_updates(
@@ -802,4 +1048,115 @@ impl CredentialService for CredentialServiceImpl {
     ) -> Result<Response<Self::StreamCredentialUpdatesStream>, Status> {
         Err(Status::unimplemented("stream_credential_updates not implemented"))
     }
-}
\ No newline at end of file
+
+    async fn get_scope_status(
+        &self,
+        request: Request<GetScopeStatusRequest>,
+    ) -> Result<Response<GetScopeStatusResponse>, Status> {
+        if request.into_inner().recheck {
+            self.scope_check.check_all().await
+                .map_err(|e| Status::internal(format!("Failed to check scopes: {}", e)))?;
+        }
+        let Some((reports, checked_at)) = self.scope_check.last_reports() else {
+            return Ok(Response::new(GetScopeStatusResponse::default()));
+        };
+
+        Ok(Response::new(GetScopeStatusResponse {
+            credentials: reports.iter().map(Self::scope_report_to_proto).collect(),
+            degraded_features: self.scope_check.degraded_features().into_iter()
+                .map(|d| DegradedFeature {
+                    name: d.feature.name.to_string(),
+                    description: d.feature.description.to_string(),
+                    missing_scopes: d.missing,
+                    reauth_commands: d.reauth_commands,
+                })
+                .collect(),
+            checked_at: Some(prost_types::Timestamp {
+                seconds: checked_at.timestamp(),
+                nanos: checked_at.timestamp_subsec_nanos() as i32,
+            }),
+        }))
+    }
+
+    async fn list_chat_identities(
+        &self,
+        request: Request<ListChatIdentitiesRequest>,
+    ) -> Result<Response<ListChatIdentitiesResponse>, Status> {
+        let wanted = request.into_inner().platform.to_lowercase();
+        let identities = self.chat_identities.identities();
+        let mut out = Vec::new();
+        for (platform, channel, account) in identities.entries() {
+            if !wanted.is_empty() && platform != wanted {
+                continue;
+            }
+            let credential_id = match maowbot_common::models::platform::Platform::from_str(platform) {
+                Ok(p) => self.chat_identities.find_account(&p, account).await
+                    .map_err(|e| Status::internal(format!("Failed to look up {}: {}", account, e)))?
+                    .map(|c| c.credential_id.to_string()),
+                Err(_) => None,
+            };
+            out.push(ChatIdentity {
+                platform: platform.to_string(),
+                channel: channel.to_string(),
+                account: account.to_string(),
+                credential_id: credential_id.unwrap_or_default(),
+            });
+        }
+        Ok(Response::new(ListChatIdentitiesResponse { identities: out }))
+    }
+
+    async fn set_chat_identity(
+        &self,
+        request: Request<SetChatIdentityRequest>,
+    ) -> Result<Response<ChatIdentity>, Status> {
+        let req = request.into_inner();
+        let platform = maowbot_common::models::platform::Platform::from_str(&req.platform)
+            .map_err(Status::invalid_argument)?;
+        let channel = req.channel.trim();
+        if channel.is_empty() {
+            return Err(Status::invalid_argument("Name a channel, or * for the platform's default"));
+        }
+        let account = Some(req.account.trim()).filter(|a| !a.is_empty());
+
+        let credential = self.chat_identities.assign(&platform, channel, account).await
+            .map_err(|e| match e {
+                maowbot_core::Error::NotFound(msg) => Status::not_found(msg),
+                other => Status::internal(other.to_string()),
+            })?;
+        info!("Chat identity for {} {} set to {:?}", platform, channel, credential.as_ref().map(|c| &c.user_name));
+        Ok(Response::new(ChatIdentity {
+            platform: platform.to_string(),
+            channel: channel.trim_start_matches('#').to_lowercase(),
+            account: credential.as_ref().map(|c| c.user_name.clone()).unwrap_or_default(),
+            credential_id: credential.map(|c| c.credential_id.to_string()).unwrap_or_default(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_status_flags_expired_and_expiring_credentials() {
+        let now = Utc::now();
+        let within = chrono::Duration::hours(72);
+        assert_eq!(expiry_status(Some(now - chrono::Duration::minutes(1)), now, within), (-60, true, false));
+        assert_eq!(expiry_status(Some(now + chrono::Duration::hours(2)), now, within), (7200, false, true));
+        assert!(!expiry_status(Some(now + chrono::Duration::days(30)), now, within).2);
+        assert_eq!(expiry_status(None, now, within), (0, false, false));
+    }
+
+    #[test]
+    fn test_validation_falls_back_to_expiry_when_the_platform_is_not_asked() {
+        assert_eq!(validation_outcome(None, false), (true, false, String::new()));
+        assert_eq!(validation_outcome(None, true), (false, false, String::new()));
+        assert_eq!(validation_outcome(Some(Ok(true)), true), (true, true, String::new()));
+        assert_eq!(validation_outcome(Some(Ok(false)), false), (false, true, "Rejected by platform".to_string()));
+
+        let (valid, checked, error) =
+            validation_outcome(Some(Err(maowbot_core::Error::Platform("timeout".into()))), false);
+        assert!(valid && !checked);
+        assert!(error.contains("timeout"));
+    }
+}
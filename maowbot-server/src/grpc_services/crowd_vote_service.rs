@@ -0,0 +1,78 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    crowd_vote_service_server::CrowdVoteService,
+    CrowdVote, CrowdVoteOption, CrowdVoteResponse, StartVoteRequest, VoteChannelRequest,
+};
+use maowbot_core::services::twitch::crowd_vote_service::{
+    self as model, CrowdVoteService as CrowdVotes, NewCrowdVote,
+};
+use std::sync::Arc;
+use tracing::info;
+use super::convert::{to_timestamp, to_status};
+
+pub struct CrowdVoteServiceImpl {
+    votes: Arc<CrowdVotes>,
+}
+
+impl CrowdVoteServiceImpl {
+    pub fn new(votes: Arc<CrowdVotes>) -> Self {
+        Self { votes }
+    }
+}
+
+fn vote_response(v: model::CrowdVote) -> Response<CrowdVoteResponse> {
+    Response::new(CrowdVoteResponse {
+        vote: Some(CrowdVote {
+            vote_id: v.vote_id.to_string(),
+            channel: v.channel,
+            title: v.title,
+            options: v.options.into_iter().map(|o| CrowdVoteOption {
+                label: o.label,
+                control: o.control.unwrap_or_default(),
+                votes: o.votes,
+                weight: o.weight,
+            }).collect(),
+            status: v.status.as_str().to_string(),
+            winner: v.winner.unwrap_or_default(),
+            started_at: Some(to_timestamp(v.started_at)),
+            ends_at: Some(to_timestamp(v.ends_at)),
+        }),
+    })
+}
+
+fn channel_of(request: Request<VoteChannelRequest>) -> Option<String> {
+    Some(request.into_inner().channel).filter(|c| !c.trim().is_empty())
+}
+
+#[tonic::async_trait]
+impl CrowdVoteService for CrowdVoteServiceImpl {
+    async fn start_vote(&self, request: Request<StartVoteRequest>) -> Result<Response<CrowdVoteResponse>, Status> {
+        let req = request.into_inner();
+        info!("Starting crowd vote '{}'", req.title);
+        let vote = self.votes.start_vote(NewCrowdVote {
+            title: req.title,
+            channel: Some(req.channel).filter(|c| !c.trim().is_empty()),
+            duration_seconds: req.duration_seconds,
+            options: req.options.into_iter().map(|o| model::CrowdVoteOption {
+                label: o.label,
+                control: Some(o.control).filter(|c| !c.trim().is_empty()),
+            }).collect(),
+        }).await.map_err(to_status)?;
+        Ok(vote_response(vote))
+    }
+
+    async fn get_vote(&self, request: Request<VoteChannelRequest>) -> Result<Response<CrowdVoteResponse>, Status> {
+        let vote = self.votes.get_vote(channel_of(request).as_deref()).await.map_err(to_status)?;
+        Ok(vote_response(vote))
+    }
+
+    async fn end_vote(&self, request: Request<VoteChannelRequest>) -> Result<Response<CrowdVoteResponse>, Status> {
+        let vote = self.votes.end_vote(channel_of(request).as_deref()).await.map_err(to_status)?;
+        Ok(vote_response(vote))
+    }
+
+    async fn cancel_vote(&self, request: Request<VoteChannelRequest>) -> Result<Response<CrowdVoteResponse>, Status> {
+        let vote = self.votes.cancel_vote(channel_of(request).as_deref()).await.map_err(to_status)?;
+        Ok(vote_response(vote))
+    }
+}
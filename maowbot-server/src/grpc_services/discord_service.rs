@@ -1,23 +1,23 @@
 use tonic::{Request, Response, Status};
 use maowbot_proto::maowbot::services::{discord_service_server::DiscordService, *};
-use maowbot_proto::maowbot::common;
+use maowbot_common::traits::repository_traits::DiscordRepository;
 use maowbot_core::plugins::manager::PluginManager;
-use maowbot_core::repositories::postgres::discord::PostgresDiscordRepository;
 use maowbot_common::traits::api::DiscordApi;
 use std::sync::Arc;
 use std::collections::HashMap;
 use chrono::Utc;
-use tracing::{info, error, debug};
+use tracing::{info, debug};
 use prost_types;
 use uuid::Uuid;
 
 pub struct DiscordServiceImpl {
     plugin_manager: Arc<PluginManager>,
-    discord_repo: Arc<PostgresDiscordRepository>,
+    #[allow(dead_code)]
+    discord_repo: Arc<dyn DiscordRepository + Send + Sync>,
 }
 
 impl DiscordServiceImpl {
-    pub fn new(plugin_manager: Arc<PluginManager>, discord_repo: Arc<PostgresDiscordRepository>) -> Self {
+    pub fn new(plugin_manager: Arc<PluginManager>, discord_repo: Arc<dyn DiscordRepository + Send + Sync>) -> Self {
         Self {
             plugin_manager,
             discord_repo,
@@ -210,7 +210,7 @@ impl DiscordService for DiscordServiceImpl {
         
         // Generate mock response data
         let message_id = Uuid::new_v4().to_string();
-        let sent_at = Utc::now();
+        let _sent_at = Utc::now();
         
         Ok(Response::new(SendEmbedResponse {
             message_id,
@@ -227,7 +227,7 @@ impl DiscordService for DiscordServiceImpl {
         // Convert to proto format
         let roles: Vec<Role> = role_tuples.into_iter()
             .map(|(role_id, role_name)| Role {
-                role_id: role_id,
+                role_id,
                 guild_id: req.guild_id.clone(),
                 name: role_name,
                 color: 0,
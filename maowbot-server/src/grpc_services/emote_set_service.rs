@@ -0,0 +1,67 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    emote_set_service_server::EmoteSetService,
+    ThirdPartyEmote, ListChannelEmotesRequest, ListChannelEmotesResponse,
+};
+use maowbot_core::services::emote_sets::{ChannelEmotes, EmoteSetService as EmoteSets};
+use maowbot_core::services::emote_stats::emotes::KnownEmote;
+use std::sync::Arc;
+use super::convert::to_status;
+
+pub struct EmoteSetServiceImpl {
+    sets: Arc<EmoteSets>,
+}
+
+impl EmoteSetServiceImpl {
+    pub fn new(sets: Arc<EmoteSets>) -> Self {
+        Self { sets }
+    }
+
+    async fn channel(&self, requested: &str) -> Result<String, Status> {
+        match requested.trim() {
+            "" => self.sets.default_channel().await.map_err(to_status),
+            channel => Ok(channel.trim_start_matches('#').to_lowercase()),
+        }
+    }
+}
+
+fn emote_to_proto(e: &KnownEmote, global: bool) -> ThirdPartyEmote {
+    ThirdPartyEmote {
+        provider: e.provider.to_string(),
+        emote_id: e.id.clone(),
+        emote_name: e.name.clone(),
+        image_url: e.provider.image_url(&e.id),
+        global,
+    }
+}
+
+fn to_response(sets: ChannelEmotes, include_globals: bool) -> ListChannelEmotesResponse {
+    let globals = if include_globals { sets.globals.as_slice() } else { &[] };
+    ListChannelEmotesResponse {
+        emotes: globals.iter().map(|e| emote_to_proto(e, true))
+            .chain(sets.emotes.iter().map(|e| emote_to_proto(e, false)))
+            .collect(),
+        channel: sets.channel,
+        fetched_at: sets.fetched_at.map(|at| prost_types::Timestamp {
+            seconds: at.timestamp(),
+            nanos: at.timestamp_subsec_nanos() as i32,
+        }),
+    }
+}
+
+#[tonic::async_trait]
+impl EmoteSetService for EmoteSetServiceImpl {
+    async fn list_channel_emotes(&self, request: Request<ListChannelEmotesRequest>) -> Result<Response<ListChannelEmotesResponse>, Status> {
+        let req = request.into_inner();
+        let channel = self.channel(&req.channel).await?;
+        let sets = self.sets.channel_emotes(&channel).await.map_err(to_status)?;
+        Ok(Response::new(to_response(sets, req.include_globals)))
+    }
+
+    async fn refresh_channel_emotes(&self, request: Request<ListChannelEmotesRequest>) -> Result<Response<ListChannelEmotesResponse>, Status> {
+        let req = request.into_inner();
+        let channel = self.channel(&req.channel).await?;
+        let sets = self.sets.refresh(&channel).await.map_err(to_status)?;
+        Ok(Response::new(to_response(sets, req.include_globals)))
+    }
+}
@@ -0,0 +1,51 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    emote_stats_service_server::EmoteStatsService,
+    EmoteStat, GetEmoteStatsRequest, GetEmoteStatsResponse,
+};
+use maowbot_core::services::emote_stats::{EmoteStatsService as EmoteStats, StatsWindow, ROLLING_MINUTES};
+use std::sync::Arc;
+use super::convert::to_status;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+pub struct EmoteStatsServiceImpl {
+    stats: Arc<EmoteStats>,
+}
+
+impl EmoteStatsServiceImpl {
+    pub fn new(stats: Arc<EmoteStats>) -> Self {
+        Self { stats }
+    }
+}
+
+#[tonic::async_trait]
+impl EmoteStatsService for EmoteStatsServiceImpl {
+    async fn get_emote_stats(&self, request: Request<GetEmoteStatsRequest>) -> Result<Response<GetEmoteStatsResponse>, Status> {
+        let req = request.into_inner();
+        let window = match (req.days, req.window_minutes) {
+            (d, _) if d > 0 => StatsWindow::Days(d as u32),
+            (_, m) if m > 0 => StatsWindow::Minutes(m as u32),
+            _ => StatsWindow::Minutes(ROLLING_MINUTES),
+        };
+        let limit = if req.limit > 0 { (req.limit as usize).min(MAX_LIMIT) } else { DEFAULT_LIMIT };
+        let channel = match req.channel.trim() {
+            "" => self.stats.default_channel().await.map_err(to_status)?,
+            channel => channel.trim_start_matches('#').to_lowercase(),
+        };
+
+        let usage = self.stats.top_emotes(&channel, window, limit).await.map_err(to_status)?;
+        Ok(Response::new(GetEmoteStatsResponse {
+            channel,
+            window: window.describe(),
+            emotes: usage.into_iter().map(|u| EmoteStat {
+                provider: u.provider.to_string(),
+                image_url: u.provider.image_url(&u.emote_id),
+                emote_id: u.emote_id,
+                emote_name: u.emote_name,
+                uses: u.uses,
+            }).collect(),
+        }))
+    }
+}
@@ -3,14 +3,19 @@ use std::sync::Arc;
 use tracing::{info, error, debug};
 use maowbot_proto::maowbot::services::event_pipeline::event_pipeline_service_server::EventPipelineService as GrpcEventPipelineService;
 use maowbot_proto::maowbot::services::event_pipeline::*;
-use maowbot_common::traits::event_pipeline_traits::{
-    EventPipelineRepository, PipelineExecutionLogRepository,
-};
 use maowbot_common::models::event_pipeline::{
     EventPipeline as DbPipeline, PipelineFilter as DbFilter, PipelineAction as DbAction,
+    PipelineExecutionLog as DbExecutionLog, PipelineExecutionStatus,
+    PipelineThrottle as DbThrottle,
+};
+use maowbot_core::eventbus::BotEvent;
+use maowbot_core::services::event_pipeline::Condition;
+use maowbot_core::platforms::discord::runtime::DiscordMessageEvent;
+use maowbot_core::platforms::twitch_eventsub::simulate::{
+    merge_json, simulate_notification, EVENT_TYPE_SHORTCUTS, SIMULATED_EVENT_TYPES,
 };
+use std::collections::HashMap;
 use uuid::Uuid;
-use chrono::Utc;
 
 use crate::context::ServerContext;
 
@@ -23,6 +28,101 @@ impl EventPipelineServiceImpl {
         Self { ctx }
     }
     
+    /// Parse the optional overrides JSON of a simulate request.
+    fn parse_overrides(overrides_json: &str) -> Result<Option<serde_json::Value>, String> {
+        if overrides_json.trim().is_empty() {
+            return Ok(None);
+        }
+        match serde_json::from_str::<serde_json::Value>(overrides_json) {
+            Ok(v) if v.is_object() => Ok(Some(v)),
+            Ok(_) => Err("Overrides must be a JSON object".to_string()),
+            Err(e) => Err(format!("Invalid overrides JSON: {}", e)),
+        }
+    }
+    
+    /// Feed a synthetic Discord chat message through the message service, like the
+    /// Discord runtime loop does for real messages.
+    async fn simulate_discord_message(
+        &self,
+        overrides: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        let mut body = serde_json::json!({
+            "channel": "general",
+            "user_id": "3000003",
+            "username": "test_viewer",
+            "text": "!ping",
+            "user_roles": [],
+            "guild_id": null,
+        });
+        if let Some(overrides) = overrides {
+            merge_json(&mut body, overrides);
+        }
+        let msg_event: DiscordMessageEvent = serde_json::from_value(body.clone())
+            .map_err(|e| format!("Event does not parse as a Discord message: {}", e))?;
+        
+        self.ctx.message_service
+            .process_incoming_message(
+                "discord",
+                &msg_event.channel,
+                &msg_event.user_id,
+                Some(&msg_event.username),
+                &msg_event.user_roles,
+                &msg_event.text,
+                &msg_event.metadata(),
+            )
+            .await
+            .map_err(|e| format!("Message service rejected the message: {}", e))?;
+        Ok(body)
+    }
+    
+    /// Run a synthetic Discord slash command the way the Discord runtime loop
+    /// does for real interactions, returning the event and the replies.
+    async fn simulate_discord_slash(
+        &self,
+        overrides: Option<&serde_json::Value>,
+    ) -> Result<(serde_json::Value, Vec<String>), String> {
+        let mut body = serde_json::json!({
+            "channel": "general",
+            "user_id": "3000003",
+            "username": "test_viewer",
+            "command": "ping",
+            "options": {},
+            "user_roles": [],
+        });
+        if let Some(overrides) = overrides {
+            merge_json(&mut body, overrides);
+        }
+        let text = |key: &str| body.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let command = text("command").trim_start_matches('/').to_string();
+        if command.is_empty() {
+            return Err("A slash command needs \"command\"".to_string());
+        }
+        let options: Vec<(String, String)> = body.get("options")
+            .and_then(|o| o.as_object())
+            .map(|o| o.iter()
+                .map(|(name, value)| (name.clone(), value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())))
+                .collect())
+            .unwrap_or_default();
+        let user_roles: Vec<String> = body.get("user_roles")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+
+        let replies = self.ctx.message_service
+            .process_slash_command(
+                "discord",
+                &text("channel"),
+                &text("user_id"),
+                Some(&text("username")),
+                &user_roles,
+                &command,
+                &options,
+            )
+            .await
+            .map_err(|e| format!("Message service rejected the slash command: {}", e))?;
+        Ok((body, replies))
+    }
+
     fn db_pipeline_to_proto(pipeline: &DbPipeline) -> Pipeline {
         Pipeline {
             pipeline_id: pipeline.pipeline_id.to_string(),
@@ -71,7 +171,119 @@ impl EventPipelineServiceImpl {
             retry_delay_ms: action.retry_delay_ms,
             created_at: action.created_at.to_rfc3339(),
             updated_at: action.updated_at.to_rfc3339(),
+            condition_type: action.condition_type.clone(),
+            condition_config: action.condition_config.as_ref().map(|c| c.to_string()),
+        }
+    }
+    
+    /// Checks an action condition from a request so that a bad expression is
+    /// refused here instead of keeping the whole pipeline from loading.
+    fn db_throttle_to_proto(throttle: &DbThrottle) -> PipelineThrottle {
+        PipelineThrottle {
+            pipeline_id: throttle.pipeline_id.to_string(),
+            cooldown_seconds: throttle.cooldown_seconds,
+            user_cooldown_seconds: throttle.user_cooldown_seconds,
+            max_executions: throttle.max_executions,
+            max_executions_per_user: throttle.max_executions_per_user,
+            window_seconds: throttle.window_seconds,
+        }
+    }
+    
+    /// Check a requested throttle: no negatives, nothing past a week, and a
+    /// window whenever a run limit is set.
+    fn parse_throttle(throttle: &PipelineThrottle) -> Result<DbThrottle, String> {
+        let pipeline_id = Uuid::parse_str(&throttle.pipeline_id)
+            .map_err(|e| format!("Invalid pipeline ID: {}", e))?;
+        let seconds = [
+            ("cooldown", throttle.cooldown_seconds),
+            ("user_cooldown", throttle.user_cooldown_seconds),
+            ("window", throttle.window_seconds),
+        ];
+        for (name, value) in seconds {
+            if !(0..=DbThrottle::MAX_SECONDS).contains(&value) {
+                return Err(format!("{} must be between 0 and {} seconds", name, DbThrottle::MAX_SECONDS));
+            }
+        }
+        if throttle.max_executions < 0 || throttle.max_executions_per_user < 0 {
+            return Err("Run limits cannot be negative".to_string());
         }
+        if (throttle.max_executions > 0 || throttle.max_executions_per_user > 0) && throttle.window_seconds == 0 {
+            return Err("A run limit needs a window (window=<seconds>)".to_string());
+        }
+        Ok(DbThrottle {
+            pipeline_id,
+            cooldown_seconds: throttle.cooldown_seconds,
+            user_cooldown_seconds: throttle.user_cooldown_seconds,
+            max_executions: throttle.max_executions,
+            max_executions_per_user: throttle.max_executions_per_user,
+            window_seconds: throttle.window_seconds,
+        })
+    }
+    
+    fn parse_action_condition(
+        condition_type: &str,
+        condition_config: Option<&str>,
+    ) -> Result<(Option<String>, Option<serde_json::Value>), String> {
+        let condition_type = condition_type.trim();
+        if condition_type.is_empty() {
+            return Ok((None, None));
+        }
+        let config: Option<serde_json::Value> = match condition_config.map(str::trim).filter(|c| !c.is_empty()) {
+            Some(text) => Some(serde_json::from_str(text)
+                .map_err(|e| format!("Invalid condition configuration JSON: {}", e))?),
+            None => None,
+        };
+        let expression_field = match condition_type {
+            "previous_success" | "previous_failure" => None,
+            "expression" => Some("if"),
+            "data_check" => Some("key"),
+            other => return Err(format!(
+                "Unknown condition type '{}' (expression, data_check, previous_success, previous_failure)", other
+            )),
+        };
+        if let Some(field) = expression_field {
+            let expression = config.as_ref()
+                .and_then(|c| c.get(field))
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("A {} condition needs \"{}\" in its configuration", condition_type, field))?;
+            Condition::parse(expression).map_err(|e| e.to_string())?;
+        }
+        Ok((Some(condition_type.to_string()), config))
+    }
+    
+    fn db_execution_to_proto(exec: DbExecutionLog, pipeline_name: String) -> ExecutionLog {
+        let action_results: Vec<ActionResult> = exec.action_results
+            .iter()
+            .map(|result| ActionResult {
+                action_id: result.action_id.to_string(),
+                action_type: result.action_type.clone(),
+                status: format!("{:?}", result.status),
+                output: result.output_data.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+                error: result.error_message.clone().unwrap_or_default(),
+                started_at: result.started_at.to_rfc3339(),
+                completed_at: result.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+            })
+            .collect();
+        
+        ExecutionLog {
+            execution_id: exec.execution_id.to_string(),
+            pipeline_id: exec.pipeline_id.to_string(),
+            pipeline_name,
+            event_type: exec.event_type,
+            event_data: exec.event_data.to_string(),
+            status: format!("{:?}", exec.status),
+            error_message: exec.error_message.unwrap_or_default(),
+            started_at: exec.started_at.to_rfc3339(),
+            completed_at: exec.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+            action_results,
+        }
+    }
+    
+    /// Map of pipeline_id -> name, used to label execution logs
+    async fn pipeline_names(&self) -> HashMap<Uuid, String> {
+        self.ctx.event_pipeline_service.repository.list_pipelines(false).await
+            .map(|pipelines| pipelines.into_iter().map(|p| (p.pipeline_id, p.name)).collect())
+            .unwrap_or_default()
     }
 }
 
@@ -139,7 +351,7 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
         };
         
         // Get existing pipeline
-        let existing = match self.ctx.event_pipeline_service.repository.get_pipeline(pipeline_id).await {
+        let _existing = match self.ctx.event_pipeline_service.repository.get_pipeline(pipeline_id).await {
             Ok(Some(p)) => p,
             Ok(None) => {
                 return Ok(Response::new(UpdatePipelineResponse {
@@ -607,6 +819,20 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
             }
         };
         
+        let (condition_type, condition_config) = match Self::parse_action_condition(
+            req.condition_type.as_deref().unwrap_or_default(),
+            req.condition_config.as_deref(),
+        ) {
+            Ok(condition) => condition,
+            Err(message) => {
+                return Ok(Response::new(AddActionResponse {
+                    success: false,
+                    message,
+                    action: None,
+                }));
+            }
+        };
+        
         use maowbot_common::models::event_pipeline::CreateActionRequest as DbCreateActionRequest;
         
         let action_request = DbCreateActionRequest {
@@ -618,8 +844,8 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
             timeout_ms: req.timeout_ms,
             retry_count: req.retry_count,
             retry_delay_ms: req.retry_delay_ms,
-            condition_type: None,
-            condition_config: None,
+            condition_type,
+            condition_config,
         };
         
         match self.ctx.event_pipeline_service.repository.add_action(pipeline_id, &action_request).await {
@@ -682,6 +908,20 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
             }
         };
         
+        let (condition_type, condition_config) = match req.condition_type.as_deref() {
+            Some(condition_type) => match Self::parse_action_condition(condition_type, req.condition_config.as_deref()) {
+                Ok(condition) => condition,
+                Err(message) => {
+                    return Ok(Response::new(UpdateActionResponse {
+                        success: false,
+                        message,
+                        action: None,
+                    }));
+                }
+            },
+            None => (existing.condition_type.clone(), existing.condition_config.clone()),
+        };
+        
         use maowbot_common::models::event_pipeline::CreateActionRequest as DbCreateActionRequest;
         
         let action_request = DbCreateActionRequest {
@@ -697,8 +937,8 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
             timeout_ms: req.timeout_ms.or(existing.timeout_ms),
             retry_count: req.retry_count.unwrap_or(existing.retry_count),
             retry_delay_ms: req.retry_delay_ms.unwrap_or(existing.retry_delay_ms),
-            condition_type: existing.condition_type.clone(),
-            condition_config: existing.condition_config.clone(),
+            condition_type,
+            condition_config,
         };
         
         match self.ctx.event_pipeline_service.repository.update_action(action_id, &action_request).await {
@@ -860,6 +1100,18 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
                 description: "Prevent rapid repeated executions".to_string(),
                 config_schema: r#"{"type":"object","properties":{"cooldown_seconds":{"type":"integer"},"per_user":{"type":"boolean"},"per_channel":{"type":"boolean"}}}"#.to_string(),
             },
+            FilterType {
+                id: "donation_amount_filter".to_string(),
+                name: "Donation Amount Filter".to_string(),
+                description: "Filter donations by amount in the base currency".to_string(),
+                config_schema: r#"{"type":"object","properties":{"min_amount":{"type":"number"},"max_amount":{"type":"number"}}}"#.to_string(),
+            },
+            FilterType {
+                id: "expression_filter".to_string(),
+                name: "Expression Filter".to_string(),
+                description: "Pass events whose variables match a condition, e.g. bits >= 1000".to_string(),
+                config_schema: r#"{"type":"object","required":["if"],"properties":{"if":{"type":"string"}}}"#.to_string(),
+            },
         ];
         
         Ok(Response::new(GetAvailableFiltersResponse {
@@ -954,6 +1206,34 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
                 config_schema: r#"{"type":"object","properties":{"provider_id":{"type":"string"},"model":{"type":"string"},"system_prompt":{"type":"string"},"prompt_template":{"type":"string"},"max_tokens":{"type":"integer"},"temperature":{"type":"number"},"send_response":{"type":"boolean"},"response_prefix":{"type":"string"}}}"#.to_string(),
                 is_parallelizable: false,
             },
+            ActionType {
+                id: "post_status".to_string(),
+                name: "Post Status".to_string(),
+                description: "Post a status to Bluesky and/or Mastodon, e.g. when the stream goes live".to_string(),
+                config_schema: r#"{"type":"object","properties":{"networks":{"type":"array","items":{"type":"string","enum":["bluesky","mastodon"]}},"message_template":{"type":"string"}}}"#.to_string(),
+                is_parallelizable: true,
+            },
+            ActionType {
+                id: "branch".to_string(),
+                name: "Branch".to_string(),
+                description: "Check a condition on the pipeline variables and jump ahead to another action or stop".to_string(),
+                config_schema: r#"{"type":"object","required":["if"],"properties":{"if":{"type":"string"},"then":{"type":["integer","string"]},"else":{"type":["integer","string"]}}}"#.to_string(),
+                is_parallelizable: false,
+            },
+            ActionType {
+                id: "stop".to_string(),
+                name: "Stop Pipeline".to_string(),
+                description: "Skip the remaining actions (give it a condition to stop only for some events)".to_string(),
+                config_schema: r#"{"type":"object","properties":{"reason":{"type":"string"}}}"#.to_string(),
+                is_parallelizable: true,
+            },
+            ActionType {
+                id: "http_request".to_string(),
+                name: "HTTP Request".to_string(),
+                description: "Call a webhook or API with a templated JSON body and keep the response for later actions".to_string(),
+                config_schema: r#"{"type":"object","required":["url"],"properties":{"method":{"type":"string"},"url":{"type":"string"},"headers":{"type":"object","additionalProperties":{"type":"string"}},"body":{},"timeout_ms":{"type":"integer"},"retries":{"type":"integer","maximum":5},"retry_delay_ms":{"type":"integer"},"capture_as":{"type":"string"},"capture":{"type":"object","additionalProperties":{"type":"string"}}}}"#.to_string(),
+                is_parallelizable: false,
+            },
         ];
         
         Ok(Response::new(GetAvailableActionsResponse {
@@ -990,50 +1270,37 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
             None
         };
         
+        let status_filter = if req.errors_only { Some(PipelineExecutionStatus::Failed) } else { None };
+        
         // Use the appropriate repository method
         let executions_result = if let Some(pipeline_id) = pipeline_id {
-            self.ctx.event_pipeline_service.repository.list_executions_for_pipeline(pipeline_id, limit, None).await
+            self.ctx.event_pipeline_service.repository.list_executions_for_pipeline(pipeline_id, limit + offset, status_filter).await
         } else {
-            self.ctx.event_pipeline_service.repository.list_recent_executions(limit).await
+            self.ctx.event_pipeline_service.repository.list_recent_executions(limit + offset).await
+                .map(|execs| execs.into_iter()
+                    .filter(|e| !req.errors_only || e.status == PipelineExecutionStatus::Failed)
+                    .collect())
         };
         
         match executions_result {
             Ok(executions) => {
-                let mut proto_executions = Vec::new();
+                let names = self.pipeline_names().await;
+                let total_count = executions.len() as i32;
                 
-                for exec in executions {
-                    let action_results: Vec<ActionResult> = exec.action_results
-                        .iter()
-                        .map(|result| ActionResult {
-                            action_id: result.action_id.to_string(),
-                            action_type: result.action_type.clone(),
-                            status: format!("{:?}", result.status),
-                            output: result.output_data.as_ref().map(|v| v.to_string()).unwrap_or_default(),
-                            error: result.error_message.clone().unwrap_or_default(),
-                            started_at: result.started_at.to_rfc3339(),
-                            completed_at: result.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
-                        })
-                        .collect();
-                    
-                    proto_executions.push(ExecutionLog {
-                        execution_id: exec.execution_id.to_string(),
-                        pipeline_id: exec.pipeline_id.to_string(),
-                        pipeline_name: "".to_string(), // TODO: Get from join
-                        event_type: exec.event_type,
-                        event_data: exec.event_data.to_string(),
-                        status: format!("{:?}", exec.status),
-                        error_message: exec.error_message.unwrap_or_default(),
-                        started_at: exec.started_at.to_rfc3339(),
-                        completed_at: exec.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
-                        action_results,
-                    });
-                }
+                let proto_executions: Vec<ExecutionLog> = executions
+                    .into_iter()
+                    .skip(offset as usize)
+                    .map(|exec| {
+                        let name = names.get(&exec.pipeline_id).cloned().unwrap_or_default();
+                        Self::db_execution_to_proto(exec, name)
+                    })
+                    .collect();
                 
                 Ok(Response::new(GetExecutionHistoryResponse {
                     success: true,
                     message: format!("Found {} executions", proto_executions.len()),
                     executions: proto_executions,
-                    total_count: 0, // TODO: Get total count
+                    total_count,
                 }))
             }
             Err(e) => {
@@ -1068,31 +1335,8 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
         
         match self.ctx.event_pipeline_service.repository.get_execution(execution_id).await {
             Ok(Some(exec)) => {
-                let action_results: Vec<ActionResult> = exec.action_results
-                    .iter()
-                    .map(|result| ActionResult {
-                        action_id: result.action_id.to_string(),
-                        action_type: result.action_type.clone(),
-                        status: format!("{:?}", result.status),
-                        output: result.output_data.as_ref().map(|v| v.to_string()).unwrap_or_default(),
-                        error: result.error_message.clone().unwrap_or_default(),
-                        started_at: result.started_at.to_rfc3339(),
-                        completed_at: result.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
-                    })
-                    .collect();
-                
-                let proto_exec = ExecutionLog {
-                    execution_id: exec.execution_id.to_string(),
-                    pipeline_id: exec.pipeline_id.to_string(),
-                    pipeline_name: "".to_string(), // TODO: Get from join
-                    event_type: exec.event_type,
-                    event_data: exec.event_data.to_string(),
-                    status: format!("{:?}", exec.status),
-                    error_message: exec.error_message.unwrap_or_default(),
-                    started_at: exec.started_at.to_rfc3339(),
-                    completed_at: exec.completed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
-                    action_results,
-                };
+                let name = self.pipeline_names().await.remove(&exec.pipeline_id).unwrap_or_default();
+                let proto_exec = Self::db_execution_to_proto(exec, name);
                 
                 Ok(Response::new(GetExecutionDetailsResponse {
                     success: true,
@@ -1118,6 +1362,322 @@ impl GrpcEventPipelineService for EventPipelineServiceImpl {
         }
     }
     
+    async fn test_fire_pipeline(
+        &self,
+        request: Request<TestFirePipelineRequest>,
+    ) -> Result<Response<TestFirePipelineResponse>, Status> {
+        let req = request.into_inner();
+        info!("Test-firing pipeline {} with event type {}", req.pipeline_id, req.event_type);
+        
+        let pipeline_id = match Uuid::parse_str(&req.pipeline_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(Response::new(TestFirePipelineResponse {
+                    success: false,
+                    message: format!("Invalid pipeline ID: {}", e),
+                    filters_passed: false,
+                    execution: None,
+                }));
+            }
+        };
+        
+        let event_data: serde_json::Value = if req.event_data.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            match serde_json::from_str(&req.event_data) {
+                Ok(v) => v,
+                Err(e) => {
+                    return Ok(Response::new(TestFirePipelineResponse {
+                        success: false,
+                        message: format!("Invalid event data JSON: {}", e),
+                        filters_passed: false,
+                        execution: None,
+                    }));
+                }
+            }
+        };
+        
+        let event = match BotEvent::from_synthetic(&req.event_type, &event_data) {
+            Some(event) => event,
+            None => {
+                return Ok(Response::new(TestFirePipelineResponse {
+                    success: false,
+                    message: format!("Unsupported event type or payload for '{}'", req.event_type),
+                    filters_passed: false,
+                    execution: None,
+                }));
+            }
+        };
+        
+        match self.ctx.event_pipeline_service.test_fire(pipeline_id, event).await {
+            Ok(outcome) => {
+                let execution = match self.ctx.event_pipeline_service.repository.get_execution(outcome.execution_id).await {
+                    Ok(Some(exec)) => {
+                        let name = self.pipeline_names().await.remove(&exec.pipeline_id).unwrap_or_default();
+                        Some(Self::db_execution_to_proto(exec, name))
+                    }
+                    _ => None,
+                };
+                
+                Ok(Response::new(TestFirePipelineResponse {
+                    success: !outcome.failed,
+                    message: if !outcome.filters_passed {
+                        "Filters did not match; no actions executed".to_string()
+                    } else if outcome.failed {
+                        "Pipeline executed with errors".to_string()
+                    } else {
+                        "Pipeline executed successfully".to_string()
+                    },
+                    filters_passed: outcome.filters_passed,
+                    execution,
+                }))
+            }
+            Err(e) => {
+                error!("Failed to test-fire pipeline: {:?}", e);
+                Ok(Response::new(TestFirePipelineResponse {
+                    success: false,
+                    message: format!("Failed to test-fire pipeline: {}", e),
+                    filters_passed: false,
+                    execution: None,
+                }))
+            }
+        }
+    }
+    
+    async fn get_pipeline_throttle(
+        &self,
+        request: Request<GetPipelineThrottleRequest>,
+    ) -> Result<Response<GetPipelineThrottleResponse>, Status> {
+        let req = request.into_inner();
+        debug!("Getting throttle for pipeline {}", req.pipeline_id);
+        
+        let pipeline_id = match Uuid::parse_str(&req.pipeline_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(Response::new(GetPipelineThrottleResponse {
+                    success: false,
+                    message: format!("Invalid pipeline ID: {}", e),
+                    throttle: None,
+                }));
+            }
+        };
+        
+        match self.ctx.event_pipeline_service.repository.get_throttle(pipeline_id).await {
+            Ok(throttle) => {
+                let throttle = throttle.unwrap_or(DbThrottle { pipeline_id, ..Default::default() });
+                Ok(Response::new(GetPipelineThrottleResponse {
+                    success: true,
+                    message: if throttle.is_active() {
+                        "Throttle retrieved successfully".to_string()
+                    } else {
+                        "Pipeline is not throttled".to_string()
+                    },
+                    throttle: Some(Self::db_throttle_to_proto(&throttle)),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to get pipeline throttle: {:?}", e);
+                Ok(Response::new(GetPipelineThrottleResponse {
+                    success: false,
+                    message: format!("Failed to get pipeline throttle: {}", e),
+                    throttle: None,
+                }))
+            }
+        }
+    }
+    
+    async fn set_pipeline_throttle(
+        &self,
+        request: Request<SetPipelineThrottleRequest>,
+    ) -> Result<Response<SetPipelineThrottleResponse>, Status> {
+        let req = request.into_inner();
+        let failure = |message: String| SetPipelineThrottleResponse {
+            success: false,
+            message,
+            throttle: None,
+        };
+        
+        let throttle = match req.throttle.as_ref().map(Self::parse_throttle) {
+            Some(Ok(throttle)) => throttle,
+            Some(Err(e)) => return Ok(Response::new(failure(e))),
+            None => return Ok(Response::new(failure("No throttle given".to_string()))),
+        };
+        info!("Setting throttle for pipeline {}: {:?}", throttle.pipeline_id, throttle);
+        
+        let repository = &self.ctx.event_pipeline_service.repository;
+        match repository.get_pipeline(throttle.pipeline_id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Ok(Response::new(failure(format!("Pipeline with ID {} not found", throttle.pipeline_id))));
+            }
+            Err(e) => return Ok(Response::new(failure(format!("Failed to get pipeline: {}", e)))),
+        }
+        
+        let saved = if throttle.is_active() {
+            repository.set_throttle(&throttle).await
+        } else {
+            repository.delete_throttle(throttle.pipeline_id).await.map(|_| throttle)
+        };
+        let saved = match saved {
+            Ok(saved) => saved,
+            Err(e) => {
+                error!("Failed to set pipeline throttle: {:?}", e);
+                return Ok(Response::new(failure(format!("Failed to set pipeline throttle: {}", e))));
+            }
+        };
+        
+        // Loaded pipelines carry their throttle
+        if let Err(e) = self.ctx.event_pipeline_service.reload_pipelines().await {
+            error!("Failed to reload pipelines after throttle change: {:?}", e);
+        }
+        
+        Ok(Response::new(SetPipelineThrottleResponse {
+            success: true,
+            message: if saved.is_active() {
+                "Throttle saved".to_string()
+            } else {
+                "Throttle removed".to_string()
+            },
+            throttle: Some(Self::db_throttle_to_proto(&saved)),
+        }))
+    }
+    
+    async fn reset_pipeline_throttle(
+        &self,
+        request: Request<ResetPipelineThrottleRequest>,
+    ) -> Result<Response<ResetPipelineThrottleResponse>, Status> {
+        let req = request.into_inner();
+        info!("Resetting throttle counters for pipeline {}", req.pipeline_id);
+        
+        let pipeline_id = match Uuid::parse_str(&req.pipeline_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(Response::new(ResetPipelineThrottleResponse {
+                    success: false,
+                    message: format!("Invalid pipeline ID: {}", e),
+                    hits_cleared: 0,
+                }));
+            }
+        };
+        
+        match self.ctx.event_pipeline_service.repository.reset_throttle_hits(pipeline_id).await {
+            Ok(cleared) => Ok(Response::new(ResetPipelineThrottleResponse {
+                success: true,
+                message: format!("Cleared {} recorded runs", cleared),
+                hits_cleared: cleared,
+            })),
+            Err(e) => {
+                error!("Failed to reset pipeline throttle: {:?}", e);
+                Ok(Response::new(ResetPipelineThrottleResponse {
+                    success: false,
+                    message: format!("Failed to reset pipeline throttle: {}", e),
+                    hits_cleared: 0,
+                }))
+            }
+        }
+    }
+    
+    async fn simulate_event(
+        &self,
+        request: Request<SimulateEventRequest>,
+    ) -> Result<Response<SimulateEventResponse>, Status> {
+        let req = request.into_inner();
+        info!("Simulating {} event '{}'", req.platform, req.event_type);
+        
+        let failure = |message: String| SimulateEventResponse {
+            success: false,
+            message,
+            event_type: req.event_type.clone(),
+            event_json: String::new(),
+        };
+        
+        let overrides = match Self::parse_overrides(&req.overrides_json) {
+            Ok(o) => o,
+            Err(e) => return Ok(Response::new(failure(e))),
+        };
+        
+        match req.platform.to_lowercase().as_str() {
+            "twitch-eventsub" | "eventsub" | "twitch" => {
+                let simulated = match simulate_notification(&req.event_type, overrides.as_ref()) {
+                    Ok(s) => s,
+                    Err(e) => return Ok(Response::new(failure(e.to_string()))),
+                };
+                let event_json = simulated.message
+                    .pointer("/payload/event")
+                    .map(|e| serde_json::to_string_pretty(e).unwrap_or_default())
+                    .unwrap_or_default();
+                self.ctx.event_bus.publish(BotEvent::TwitchEventSub(simulated.event)).await;
+                
+                Ok(Response::new(SimulateEventResponse {
+                    success: true,
+                    message: format!("Published simulated {} event", simulated.sub_type),
+                    event_type: simulated.sub_type.to_string(),
+                    event_json,
+                }))
+            }
+            "discord" if matches!(req.event_type.as_str(), "slash" | "slash_command") => {
+                match self.simulate_discord_slash(overrides.as_ref()).await {
+                    Ok((body, replies)) => Ok(Response::new(SimulateEventResponse {
+                        success: true,
+                        message: format!("Slash command answered: {}", replies.join(" | ")),
+                        event_type: "slash".to_string(),
+                        event_json: serde_json::to_string_pretty(&body).unwrap_or_default(),
+                    })),
+                    Err(e) => Ok(Response::new(failure(e))),
+                }
+            }
+            "discord" => {
+                if !matches!(req.event_type.as_str(), "" | "message" | "chat_message") {
+                    return Ok(Response::new(failure(format!(
+                        "Unsupported Discord event type '{}' (supported: message, slash)", req.event_type
+                    ))));
+                }
+                match self.simulate_discord_message(overrides.as_ref()).await {
+                    Ok(body) => Ok(Response::new(SimulateEventResponse {
+                        success: true,
+                        message: "Processed simulated Discord message".to_string(),
+                        event_type: "message".to_string(),
+                        event_json: serde_json::to_string_pretty(&body).unwrap_or_default(),
+                    })),
+                    Err(e) => Ok(Response::new(failure(e))),
+                }
+            }
+            other => Ok(Response::new(failure(format!(
+                "Unsupported platform '{}' (supported: twitch-eventsub, discord)", other
+            )))),
+        }
+    }
+    
+    async fn list_simulated_events(
+        &self,
+        _request: Request<ListSimulatedEventsRequest>,
+    ) -> Result<Response<ListSimulatedEventsResponse>, Status> {
+        let mut event_types: Vec<SimulatedEventType> = SIMULATED_EVENT_TYPES
+            .iter()
+            .map(|(event_type, _)| SimulatedEventType {
+                platform: "twitch-eventsub".to_string(),
+                event_type: event_type.to_string(),
+                shortcuts: EVENT_TYPE_SHORTCUTS
+                    .iter()
+                    .filter(|(_, full)| full == event_type)
+                    .map(|(short, _)| short.to_string())
+                    .collect(),
+            })
+            .collect();
+        event_types.push(SimulatedEventType {
+            platform: "discord".to_string(),
+            event_type: "message".to_string(),
+            shortcuts: Vec::new(),
+        });
+        event_types.push(SimulatedEventType {
+            platform: "discord".to_string(),
+            event_type: "slash".to_string(),
+            shortcuts: vec!["slash_command".to_string()],
+        });
+        
+        Ok(Response::new(ListSimulatedEventsResponse { event_types }))
+    }
+    
     async fn reload_pipelines(
         &self,
         _request: Request<ReloadPipelinesRequest>,
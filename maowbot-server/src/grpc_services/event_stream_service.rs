@@ -0,0 +1,148 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tracing::debug;
+use maowbot_proto::maowbot::services::{
+    event_stream_service_server::EventStreamService,
+    SubscribeEventsRequest, UiEvent as ProtoUiEvent, UiEventKind as ProtoUiEventKind,
+};
+use maowbot_core::services::ui_events::{UiEvent, UiEventKind, UiEventStream};
+
+/// Events queued per client before the stream applies backpressure.
+const CLIENT_BUFFER: usize = 256;
+
+pub struct EventStreamServiceImpl {
+    events: Arc<UiEventStream>,
+}
+
+impl EventStreamServiceImpl {
+    pub fn new(events: Arc<UiEventStream>) -> Self {
+        Self { events }
+    }
+}
+
+fn kind_to_proto(kind: UiEventKind) -> ProtoUiEventKind {
+    match kind {
+        UiEventKind::Chat => ProtoUiEventKind::Chat,
+        UiEventKind::Alert => ProtoUiEventKind::Alert,
+        UiEventKind::Connection => ProtoUiEventKind::Connection,
+        UiEventKind::Osc => ProtoUiEventKind::Osc,
+    }
+}
+
+fn event_to_proto(event: &UiEvent, replayed: bool) -> ProtoUiEvent {
+    ProtoUiEvent {
+        resume_token: event.seq.to_string(),
+        kind: kind_to_proto(event.kind) as i32,
+        event_type: event.event_type.clone(),
+        timestamp: Some(prost_types::Timestamp {
+            seconds: event.timestamp.timestamp(),
+            nanos: event.timestamp.timestamp_subsec_nanos() as i32,
+        }),
+        fields: event.data.iter()
+            .map(|(k, v)| (k.clone(), match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            }))
+            .collect(),
+        replayed,
+    }
+}
+
+fn gap_event(resume_token: i64) -> ProtoUiEvent {
+    ProtoUiEvent {
+        resume_token: resume_token.to_string(),
+        kind: ProtoUiEventKind::Gap as i32,
+        event_type: "stream.gap".to_string(),
+        timestamp: Some(prost_types::Timestamp {
+            seconds: chrono::Utc::now().timestamp(),
+            nanos: 0,
+        }),
+        fields: Default::default(),
+        replayed: false,
+    }
+}
+
+/// Sends to the client; false once it has gone away.
+async fn send(tx: &mpsc::Sender<Result<ProtoUiEvent, Status>>, event: ProtoUiEvent) -> bool {
+    tx.send(Ok(event)).await.is_ok()
+}
+
+#[tonic::async_trait]
+impl EventStreamService for EventStreamServiceImpl {
+    type SubscribeEventsStream = Pin<Box<dyn Stream<Item = Result<ProtoUiEvent, Status>> + Send>>;
+
+    async fn subscribe_events(
+        &self,
+        request: Request<SubscribeEventsRequest>,
+    ) -> Result<Response<Self::SubscribeEventsStream>, Status> {
+        let req = request.into_inner();
+        let mut kinds = Vec::new();
+        for k in &req.kinds {
+            kinds.push(match ProtoUiEventKind::try_from(*k) {
+                Ok(ProtoUiEventKind::Chat) => UiEventKind::Chat,
+                Ok(ProtoUiEventKind::Alert) => UiEventKind::Alert,
+                Ok(ProtoUiEventKind::Connection) => UiEventKind::Connection,
+                Ok(ProtoUiEventKind::Osc) => UiEventKind::Osc,
+                _ => return Err(Status::invalid_argument(format!("Unknown event kind {}", k))),
+            });
+        }
+        let after = match req.resume_token.as_str() {
+            "" => None,
+            token => Some(token.parse::<i64>()
+                .map_err(|_| Status::invalid_argument("Invalid resume_token"))?),
+        };
+
+        let subscription = self.events.subscribe(after).await
+            .map_err(|e| Status::internal(format!("Failed to read the event journal: {}", e)))?;
+        let wanted = move |event: &UiEvent| kinds.is_empty() || kinds.contains(&event.kind);
+
+        let (tx, rx) = mpsc::channel(CLIENT_BUFFER);
+        let events = self.events.clone();
+        tokio::spawn(async move {
+            let mut last_seq = after.unwrap_or(0);
+            let mut subscription = subscription;
+            loop {
+                if subscription.gap && !send(&tx, gap_event(last_seq)).await {
+                    return;
+                }
+                for event in &subscription.backfill {
+                    last_seq = last_seq.max(event.seq);
+                    if wanted(event) && !send(&tx, event_to_proto(event, true)).await {
+                        return;
+                    }
+                }
+                // Live until the client falls too far behind, then catch up
+                // through the journal the same way a reconnect would
+                loop {
+                    match subscription.live.recv().await {
+                        Ok(event) if event.seq <= last_seq => {}
+                        Ok(event) => {
+                            last_seq = event.seq;
+                            if wanted(&event) && !send(&tx, event_to_proto(&event, false)).await {
+                                return;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            debug!("UI event subscriber lagged by {} event(s); catching up", n);
+                            break;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    }
+                }
+                subscription = match events.subscribe(Some(last_seq)).await {
+                    Ok(subscription) => subscription,
+                    Err(e) => {
+                        let _ = tx.send(Err(Status::internal(format!("Failed to read the event journal: {}", e)))).await;
+                        return;
+                    }
+                };
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
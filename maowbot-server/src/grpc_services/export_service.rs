@@ -0,0 +1,82 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tracing::info;
+use maowbot_proto::maowbot::services::{
+    export_service_server::ExportService,
+    ExportChunk as ProtoExportChunk, ExportRequest,
+};
+use maowbot_core::services::data_export::{DataExportService, ExportFormat, ExportKind, ExportQuery};
+use super::convert::{from_timestamp, to_status};
+
+/// Chunks queued per client before the export waits for it.
+const CLIENT_BUFFER: usize = 4;
+
+pub struct ExportServiceImpl {
+    exports: Arc<DataExportService>,
+}
+
+impl ExportServiceImpl {
+    pub fn new(exports: Arc<DataExportService>) -> Self {
+        Self { exports }
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    let s = s.trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+#[tonic::async_trait]
+impl ExportService for ExportServiceImpl {
+    type ExportStream = Pin<Box<dyn Stream<Item = Result<ProtoExportChunk, Status>> + Send>>;
+
+    async fn export(
+        &self,
+        request: Request<ExportRequest>,
+    ) -> Result<Response<Self::ExportStream>, Status> {
+        let req = request.into_inner();
+        let kind: ExportKind = req.kind.parse().map_err(to_status)?;
+        let format = match req.format.trim() {
+            "" => ExportFormat::Csv,
+            f => f.parse().map_err(to_status)?,
+        };
+        let until = req.until.as_ref().and_then(from_timestamp).unwrap_or_else(Utc::now);
+        let since = req.since.as_ref().and_then(from_timestamp).unwrap_or(until - Duration::days(7));
+        if since >= until {
+            return Err(Status::invalid_argument("since must be before until"));
+        }
+        let query = ExportQuery {
+            kind,
+            format,
+            since,
+            until,
+            platform: non_empty(req.platform),
+            channel: non_empty(req.channel),
+        };
+        info!("gRPC: export {} as {} from {} to {}", kind, format.as_str(), since, until);
+
+        let mut chunks = self.exports.export(query);
+        let (tx, rx) = mpsc::channel(CLIENT_BUFFER);
+        tokio::spawn(async move {
+            while let Some(chunk) = chunks.recv().await {
+                let item = chunk
+                    .map(|c| ProtoExportChunk {
+                        data: c.text.into_bytes(),
+                        rows_written: c.rows_written as i64,
+                        total_rows: c.total_rows as i64,
+                    })
+                    .map_err(to_status);
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
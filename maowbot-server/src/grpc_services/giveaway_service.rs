@@ -0,0 +1,154 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    giveaway_service_server::GiveawayService,
+    Giveaway, GiveawayEntry, GiveawayRules,
+    OpenGiveawayRequest, GiveawayIdRequest, GiveawayResponse,
+    DrawWinnersRequest, DrawWinnersResponse,
+    RerollWinnerRequest, RerollWinnerResponse,
+    ListGiveawaysRequest, ListGiveawaysResponse, GetGiveawayResponse,
+};
+use maowbot_common::models::giveaway as model;
+use maowbot_core::services::twitch::giveaway_service::{GiveawayService as Giveaways, NewGiveaway};
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+use super::convert::{to_timestamp, to_status};
+
+const DEFAULT_LIST_LIMIT: i64 = 20;
+
+pub struct GiveawayServiceImpl {
+    giveaways: Arc<Giveaways>,
+}
+
+impl GiveawayServiceImpl {
+    pub fn new(giveaways: Arc<Giveaways>) -> Self {
+        Self { giveaways }
+    }
+
+    async fn giveaway_to_proto(&self, g: model::Giveaway) -> Giveaway {
+        let entry_count = self.giveaways.list_entries(g.giveaway_id).await
+            .map(|entries| entries.len() as i32)
+            .unwrap_or_default();
+        Giveaway {
+            giveaway_id: g.giveaway_id.to_string(),
+            title: g.title,
+            channel: g.channel,
+            keyword: g.keyword.unwrap_or_default(),
+            points_cost: g.points_cost,
+            rules: Some(GiveawayRules {
+                sub_only: g.rules.sub_only,
+                min_follow_days: g.rules.min_follow_days,
+                exclude_winners_days: g.rules.exclude_winners_days,
+                sub_weight: g.rules.sub_weight,
+            }),
+            status: g.status.to_string(),
+            entry_count,
+            created_at: Some(to_timestamp(g.created_at)),
+            closed_at: g.closed_at.map(to_timestamp),
+        }
+    }
+
+    async fn giveaway_response(&self, g: model::Giveaway) -> Response<GiveawayResponse> {
+        Response::new(GiveawayResponse {
+            giveaway: Some(self.giveaway_to_proto(g).await),
+        })
+    }
+}
+
+fn entry_to_proto(e: model::GiveawayEntry) -> GiveawayEntry {
+    GiveawayEntry {
+        user_id: e.user_id.to_string(),
+        username: e.username,
+        is_subscriber: e.is_subscriber,
+        weight: e.weight,
+        is_winner: e.is_winner,
+        rerolled: e.rerolled,
+        entered_at: Some(to_timestamp(e.entered_at)),
+        won_at: e.won_at.map(to_timestamp),
+    }
+}
+
+fn parse_id(id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(id.trim()).map_err(|_| Status::invalid_argument(format!("Invalid giveaway id '{}'", id)))
+}
+
+#[tonic::async_trait]
+impl GiveawayService for GiveawayServiceImpl {
+    async fn open_giveaway(&self, request: Request<OpenGiveawayRequest>) -> Result<Response<GiveawayResponse>, Status> {
+        let req = request.into_inner();
+        info!("Opening giveaway '{}'", req.title);
+        let rules = req.rules.unwrap_or(GiveawayRules {
+            sub_only: false,
+            min_follow_days: 0,
+            exclude_winners_days: 0,
+            sub_weight: 1,
+        });
+        let giveaway = self.giveaways.open_giveaway(NewGiveaway {
+            title: req.title,
+            channel: Some(req.channel).filter(|c| !c.trim().is_empty()),
+            keyword: Some(req.keyword).filter(|k| !k.trim().is_empty()),
+            points_cost: req.points_cost,
+            rules: model::GiveawayRules {
+                sub_only: rules.sub_only,
+                min_follow_days: rules.min_follow_days,
+                exclude_winners_days: rules.exclude_winners_days,
+                sub_weight: rules.sub_weight.max(1),
+            },
+        }).await.map_err(to_status)?;
+        Ok(self.giveaway_response(giveaway).await)
+    }
+
+    async fn close_giveaway(&self, request: Request<GiveawayIdRequest>) -> Result<Response<GiveawayResponse>, Status> {
+        let id = parse_id(&request.into_inner().giveaway_id)?;
+        let giveaway = self.giveaways.close_giveaway(id).await.map_err(to_status)?;
+        Ok(self.giveaway_response(giveaway).await)
+    }
+
+    async fn cancel_giveaway(&self, request: Request<GiveawayIdRequest>) -> Result<Response<GiveawayResponse>, Status> {
+        let id = parse_id(&request.into_inner().giveaway_id)?;
+        let giveaway = self.giveaways.cancel_giveaway(id).await.map_err(to_status)?;
+        Ok(self.giveaway_response(giveaway).await)
+    }
+
+    async fn draw_winners(&self, request: Request<DrawWinnersRequest>) -> Result<Response<DrawWinnersResponse>, Status> {
+        let req = request.into_inner();
+        let id = parse_id(&req.giveaway_id)?;
+        let winners = self.giveaways.draw(id, req.count.max(1) as usize).await.map_err(to_status)?;
+        Ok(Response::new(DrawWinnersResponse {
+            winners: winners.into_iter().map(entry_to_proto).collect(),
+        }))
+    }
+
+    async fn reroll_winner(&self, request: Request<RerollWinnerRequest>) -> Result<Response<RerollWinnerResponse>, Status> {
+        let req = request.into_inner();
+        let id = parse_id(&req.giveaway_id)?;
+        let username = Some(req.username.trim()).filter(|u| !u.is_empty());
+        let winner = self.giveaways.reroll(id, username).await.map_err(to_status)?;
+        Ok(Response::new(RerollWinnerResponse {
+            winner: Some(entry_to_proto(winner)),
+        }))
+    }
+
+    async fn list_giveaways(&self, request: Request<ListGiveawaysRequest>) -> Result<Response<ListGiveawaysResponse>, Status> {
+        let req = request.into_inner();
+        let limit = if req.limit > 0 { req.limit as i64 } else { DEFAULT_LIST_LIMIT };
+        let giveaways = self.giveaways.list_giveaways(limit).await.map_err(to_status)?;
+        let mut out = Vec::with_capacity(giveaways.len());
+        for g in giveaways {
+            out.push(self.giveaway_to_proto(g).await);
+        }
+        Ok(Response::new(ListGiveawaysResponse { giveaways: out }))
+    }
+
+    async fn get_giveaway(&self, request: Request<GiveawayIdRequest>) -> Result<Response<GetGiveawayResponse>, Status> {
+        let id = parse_id(&request.into_inner().giveaway_id)?;
+        let giveaway = self.giveaways.get_giveaway(id).await.map_err(to_status)?;
+        let entries = self.giveaways.list_entries(id).await.map_err(to_status)?;
+        let mut giveaway = self.giveaway_to_proto(giveaway).await;
+        giveaway.entry_count = entries.len() as i32;
+        Ok(Response::new(GetGiveawayResponse {
+            giveaway: Some(giveaway),
+            entries: entries.into_iter().map(entry_to_proto).collect(),
+        }))
+    }
+}
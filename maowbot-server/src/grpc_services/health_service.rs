@@ -0,0 +1,52 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    health_service_server::HealthService,
+    GetReadinessRequest, GetReadinessResponse, ReadinessState, SubsystemReadiness,
+};
+use std::sync::Arc;
+
+use crate::startup::{Readiness, Startup};
+
+pub struct HealthServiceImpl {
+    startup: Arc<Startup>,
+}
+
+impl HealthServiceImpl {
+    pub fn new(startup: Arc<Startup>) -> Self {
+        Self { startup }
+    }
+}
+
+fn state_to_proto(state: Readiness) -> ReadinessState {
+    match state {
+        Readiness::Pending => ReadinessState::Pending,
+        Readiness::Starting => ReadinessState::Starting,
+        Readiness::Ready => ReadinessState::Ready,
+        Readiness::Degraded => ReadinessState::Degraded,
+        Readiness::Failed => ReadinessState::Failed,
+    }
+}
+
+#[tonic::async_trait]
+impl HealthService for HealthServiceImpl {
+    async fn get_readiness(&self, _request: Request<GetReadinessRequest>) -> Result<Response<GetReadinessResponse>, Status> {
+        let subsystems = self.startup.snapshot().into_iter()
+            .map(|(subsystem, status)| SubsystemReadiness {
+                name: subsystem.name().to_string(),
+                state: state_to_proto(status.state) as i32,
+                message: status.message,
+                depends_on: subsystem.depends_on().iter().map(|d| d.name().to_string()).collect(),
+                since: Some(prost_types::Timestamp {
+                    seconds: status.since.timestamp(),
+                    nanos: status.since.timestamp_subsec_nanos() as i32,
+                }),
+                startup_ms: status.took.map(|t| t.as_millis() as i64).unwrap_or(0),
+            })
+            .collect();
+        Ok(Response::new(GetReadinessResponse {
+            ready: self.startup.is_ready(),
+            subsystems,
+            uptime_ms: self.startup.uptime().as_millis() as i64,
+        }))
+    }
+}
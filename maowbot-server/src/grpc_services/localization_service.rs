@@ -0,0 +1,98 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    localization_service_server::LocalizationService,
+    ChannelLanguageInfo, DeleteLocalizedMessageRequest, DeleteLocalizedMessageResponse, LanguageInfo,
+    ListChannelLanguagesRequest, ListChannelLanguagesResponse, ListLanguagesRequest, ListLanguagesResponse,
+    ListMessagesRequest, ListMessagesResponse, LocalizedMessage, SetChannelLanguageRequest,
+    SetChannelLanguageResponse, SetMessagesRequest, SetMessagesResponse,
+};
+use maowbot_core::i18n::{self, bundled, Localizer, TemplateSource};
+use std::sync::Arc;
+use super::convert::to_status;
+
+pub struct LocalizationServiceImpl {
+    localizer: Arc<Localizer>,
+}
+
+impl LocalizationServiceImpl {
+    pub fn new(localizer: Arc<Localizer>) -> Self {
+        Self { localizer }
+    }
+}
+
+#[tonic::async_trait]
+impl LocalizationService for LocalizationServiceImpl {
+    async fn list_languages(&self, _request: Request<ListLanguagesRequest>) -> Result<Response<ListLanguagesResponse>, Status> {
+        let total = bundled::english().len() as i32;
+        Ok(Response::new(ListLanguagesResponse {
+            languages: self.localizer.languages().into_iter().map(|l| LanguageInfo {
+                language: l.language,
+                bundled: l.bundled,
+                custom_messages: l.custom_strings as i32,
+                translated: l.translated as i32,
+                total,
+            }).collect(),
+            default_language: self.localizer.default_language(),
+        }))
+    }
+
+    async fn list_messages(&self, request: Request<ListMessagesRequest>) -> Result<Response<ListMessagesResponse>, Status> {
+        let req = request.into_inner();
+        let language = match req.language.trim() {
+            "" => self.localizer.default_language(),
+            language => i18n::normalize_language(language).map_err(to_status)?,
+        };
+        let entries = self.localizer.entries(&language).map_err(to_status)?;
+        Ok(Response::new(ListMessagesResponse {
+            language,
+            messages: entries.into_iter().map(|e| LocalizedMessage {
+                key: e.key,
+                english: e.english,
+                text: e.template,
+                source: match e.source {
+                    TemplateSource::Custom => "custom",
+                    TemplateSource::Bundled => "bundled",
+                    TemplateSource::Fallback => "fallback",
+                }.to_string(),
+            }).collect(),
+        }))
+    }
+
+    async fn set_messages(&self, request: Request<SetMessagesRequest>) -> Result<Response<SetMessagesResponse>, Status> {
+        let req = request.into_inner();
+        if req.messages.is_empty() {
+            return Err(Status::invalid_argument("No messages given"));
+        }
+        let mut strings: Vec<(String, String)> = req.messages.into_iter().collect();
+        strings.sort();
+        let stored = self.localizer.set_strings(&req.language, &strings).await.map_err(to_status)?;
+        Ok(Response::new(SetMessagesResponse { stored: stored as i32 }))
+    }
+
+    async fn delete_message(&self, request: Request<DeleteLocalizedMessageRequest>) -> Result<Response<DeleteLocalizedMessageResponse>, Status> {
+        let req = request.into_inner();
+        let removed = self.localizer.delete_string(&req.language, &req.key).await.map_err(to_status)?;
+        Ok(Response::new(DeleteLocalizedMessageResponse { removed }))
+    }
+
+    async fn list_channel_languages(&self, _request: Request<ListChannelLanguagesRequest>) -> Result<Response<ListChannelLanguagesResponse>, Status> {
+        Ok(Response::new(ListChannelLanguagesResponse {
+            channels: self.localizer.channel_languages().into_iter().map(|c| ChannelLanguageInfo {
+                platform: c.platform,
+                channel: c.channel,
+                language: c.language,
+            }).collect(),
+        }))
+    }
+
+    async fn set_channel_language(&self, request: Request<SetChannelLanguageRequest>) -> Result<Response<SetChannelLanguageResponse>, Status> {
+        let req = request.into_inner();
+        let platform = match req.platform.trim() {
+            "" => "twitch-irc",
+            platform => platform,
+        };
+        let language = Some(req.language.trim()).filter(|l| !l.is_empty());
+        self.localizer.set_channel_language(platform, &req.channel, language).await.map_err(to_status)?;
+        Ok(Response::new(SetChannelLanguageResponse {}))
+    }
+}
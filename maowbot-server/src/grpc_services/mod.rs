@@ -15,6 +15,24 @@ pub mod osc_service;
 pub mod autostart_service;
 pub mod obs_service;
 pub mod event_pipeline_service;
+pub mod chat_archive_service;
+pub mod giveaway_service;
+pub mod crowd_vote_service;
+pub mod protection_service;
+pub mod moderation_rules_service;
+pub mod emote_stats_service;
+pub mod emote_set_service;
+pub mod localization_service;
+pub mod event_stream_service;
+pub mod ui_settings_service;
+pub mod responder_service;
+pub mod alerting_service;
+pub mod health_service;
+pub mod stream_session_service;
+pub mod export_service;
+pub mod workspace;
+pub mod paging;
+pub mod convert;
 
 // Re-export service implementations
 pub use user_service::UserServiceImpl;
@@ -31,4 +49,20 @@ pub use vrchat_service::VRChatServiceImpl;
 pub use osc_service::OscServiceImpl;
 pub use autostart_service::AutostartServiceImpl;
 pub use obs_service::ObsServiceImpl;
-pub use event_pipeline_service::EventPipelineServiceImpl;
\ No newline at end of file
+pub use event_pipeline_service::EventPipelineServiceImpl;
+pub use chat_archive_service::ChatArchiveServiceImpl;
+pub use giveaway_service::GiveawayServiceImpl;
+pub use crowd_vote_service::CrowdVoteServiceImpl;
+pub use protection_service::ProtectionServiceImpl;
+pub use moderation_rules_service::ModerationRulesServiceImpl;
+pub use emote_stats_service::EmoteStatsServiceImpl;
+pub use emote_set_service::EmoteSetServiceImpl;
+pub use localization_service::LocalizationServiceImpl;
+pub use event_stream_service::EventStreamServiceImpl;
+pub use ui_settings_service::UiSettingsServiceImpl;
+pub use responder_service::ResponderServiceImpl;
+pub use alerting_service::AlertingServiceImpl;
+pub use health_service::HealthServiceImpl;
+pub use stream_session_service::StreamSessionServiceImpl;
+pub use export_service::ExportServiceImpl;
+pub use workspace::WorkspaceResolver;
\ No newline at end of file
@@ -0,0 +1,177 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    moderation_rules_service_server::ModerationRulesService,
+    ChatRule, ChatRuleResponse, RuleHit,
+    ListRulesRequest, ListRulesResponse,
+    AddRuleRequest, UpdateRuleRequest,
+    DeleteRuleRequest, DeleteRuleResponse,
+    ResetRuleHitsRequest,
+    TestMessageRequest, TestMessageResponse,
+    CategoryOffense, ListOffensesRequest, ListOffensesResponse,
+    PardonUserRequest, PardonUserResponse,
+};
+use maowbot_common::models::moderation_rule::{ModerationRule, RuleKind, RuleSeverity};
+use maowbot_core::services::moderation::{ModerationService, NewRule, RuleEdit};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::authz::{audit_value, AuditNote, Caller};
+use super::convert::{to_timestamp, to_status};
+
+const DEFAULT_TIMEOUT_SECS: i32 = 600;
+
+pub struct ModerationRulesServiceImpl {
+    moderation: Arc<ModerationService>,
+}
+
+impl ModerationRulesServiceImpl {
+    pub fn new(moderation: Arc<ModerationService>) -> Self {
+        Self { moderation }
+    }
+}
+
+fn caller_name<T>(request: &Request<T>) -> String {
+    request.extensions().get::<Caller>()
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "console".to_string())
+}
+
+fn rule_to_proto(r: ModerationRule) -> ChatRule {
+    ChatRule {
+        rule_id: r.rule_id.to_string(),
+        name: r.name,
+        kind: r.kind.to_string(),
+        pattern: r.pattern,
+        severity: r.severity.to_string(),
+        timeout_secs: r.timeout_secs,
+        permit_subs: r.permit_subs,
+        permit_vips: r.permit_vips,
+        permit_mods: r.permit_mods,
+        enabled: r.enabled,
+        hit_count: r.hit_count,
+        last_hit_at: r.last_hit_at.map(to_timestamp),
+        category: r.category,
+    }
+}
+
+fn rule_response(rule: ModerationRule) -> Response<ChatRuleResponse> {
+    Response::new(ChatRuleResponse { rule: Some(rule_to_proto(rule)) })
+}
+
+#[tonic::async_trait]
+impl ModerationRulesService for ModerationRulesServiceImpl {
+    async fn list_rules(&self, _request: Request<ListRulesRequest>) -> Result<Response<ListRulesResponse>, Status> {
+        let rules = self.moderation.list_rules().await.map_err(to_status)?;
+        Ok(Response::new(ListRulesResponse {
+            rules: rules.into_iter().map(rule_to_proto).collect(),
+        }))
+    }
+
+    async fn add_rule(&self, request: Request<AddRuleRequest>) -> Result<Response<ChatRuleResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let kind: RuleKind = req.kind.parse().map_err(to_status)?;
+        let severity: RuleSeverity = if req.severity.is_empty() {
+            RuleSeverity::Delete
+        } else {
+            req.severity.parse().map_err(to_status)?
+        };
+        let rule = self.moderation.add_rule(NewRule {
+            name: req.name,
+            kind,
+            pattern: req.pattern,
+            severity,
+            timeout_secs: if req.timeout_secs > 0 { req.timeout_secs } else { DEFAULT_TIMEOUT_SECS },
+            permit_subs: req.permit_subs,
+            permit_vips: req.permit_vips,
+            permit_mods: req.permit_mods,
+            category: req.category,
+        }).await.map_err(to_status)?;
+        info!("Moderation rule '{}' added by '{}'", rule.name, caller);
+        audit.change(format!("moderation_rule:{}", rule.name), None, audit_value(&rule));
+        Ok(rule_response(rule))
+    }
+
+    async fn update_rule(&self, request: Request<UpdateRuleRequest>) -> Result<Response<ChatRuleResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let before = self.moderation.get_rule(&req.name).await.ok();
+        let severity = req.severity.map(|s| s.parse::<RuleSeverity>()).transpose().map_err(to_status)?;
+        let rule = self.moderation.edit_rule(&req.name, RuleEdit {
+            pattern: req.pattern,
+            severity,
+            timeout_secs: req.timeout_secs,
+            permit_subs: req.permit_subs,
+            permit_vips: req.permit_vips,
+            permit_mods: req.permit_mods,
+            category: req.category,
+            enabled: req.enabled,
+        }).await.map_err(to_status)?;
+        info!("Moderation rule '{}' updated by '{}'", rule.name, caller);
+        audit.change(format!("moderation_rule:{}", rule.name), before.as_ref().and_then(audit_value), audit_value(&rule));
+        Ok(rule_response(rule))
+    }
+
+    async fn delete_rule(&self, request: Request<DeleteRuleRequest>) -> Result<Response<DeleteRuleResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let name = request.into_inner().name;
+        let before = self.moderation.get_rule(&name).await.ok();
+        self.moderation.delete_rule(&name).await.map_err(to_status)?;
+        info!("Moderation rule '{}' deleted by '{}'", name, caller);
+        audit.change(format!("moderation_rule:{}", name), before.as_ref().and_then(audit_value), None);
+        Ok(Response::new(DeleteRuleResponse {}))
+    }
+
+    async fn reset_rule_hits(&self, request: Request<ResetRuleHitsRequest>) -> Result<Response<ChatRuleResponse>, Status> {
+        let name = request.into_inner().name;
+        let rule = self.moderation.reset_counter(&name).await.map_err(to_status)?;
+        Ok(rule_response(rule))
+    }
+
+    async fn test_message(&self, request: Request<TestMessageRequest>) -> Result<Response<TestMessageResponse>, Status> {
+        let req = request.into_inner();
+        let hits = self.moderation.test(&req.text, &req.roles);
+        Ok(Response::new(TestMessageResponse {
+            hits: hits.into_iter().map(|m| RuleHit {
+                name: m.name,
+                severity: m.severity.to_string(),
+                timeout_secs: m.timeout_secs,
+                matched: m.matched,
+            }).collect(),
+        }))
+    }
+
+    async fn list_offenses(&self, request: Request<ListOffensesRequest>) -> Result<Response<ListOffensesResponse>, Status> {
+        let user = request.into_inner().user;
+        let offenses = self.moderation.offenses(&user).await.map_err(to_status)?;
+        Ok(Response::new(ListOffensesResponse {
+            offenses: offenses.into_iter().map(|o| CategoryOffense {
+                category: o.category,
+                count: o.count as i32,
+                last_offense_at: Some(to_timestamp(o.last_offense_at)),
+                next_step: o.next_step.to_string(),
+            }).collect(),
+        }))
+    }
+
+    async fn pardon_user(&self, request: Request<PardonUserRequest>) -> Result<Response<PardonUserResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let category = Some(req.category.as_str()).filter(|c| !c.trim().is_empty());
+        let categories = self.moderation.pardon(&req.user, category, req.lift, &caller).await.map_err(to_status)?;
+        info!("'{}' pardoned by '{}' in {:?}", req.user, caller, categories);
+        audit.change(
+            format!("moderation_pardon:{}", req.user),
+            None,
+            Some(serde_json::json!({
+                "categories": categories,
+                "lift": req.lift,
+            })),
+        );
+        Ok(Response::new(PardonUserResponse { categories }))
+    }
+}
@@ -19,18 +19,17 @@ use maowbot_proto::maowbot::services::{
     GetRecordingStatusRequest, GetRecordingStatusResponse,
 };
 use maowbot_core::platforms::manager::PlatformManager;
-use maowbot_core::repositories::postgres::obs::PostgresObsRepository;
 use maowbot_common::traits::repository_traits::ObsRepository;
 use std::sync::Arc;
 use tracing::{info, error};
 
 pub struct ObsServiceImpl {
     platform_manager: Arc<PlatformManager>,
-    obs_repo: Arc<PostgresObsRepository>,
+    obs_repo: Arc<dyn ObsRepository>,
 }
 
 impl ObsServiceImpl {
-    pub fn new(platform_manager: Arc<PlatformManager>, obs_repo: Arc<PostgresObsRepository>) -> Self {
+    pub fn new(platform_manager: Arc<PlatformManager>, obs_repo: Arc<dyn ObsRepository>) -> Self {
         Self {
             platform_manager,
             obs_repo,
@@ -7,24 +7,70 @@ use maowbot_proto::maowbot::common;
 use maowbot_core::plugins::manager::PluginManager;
 use maowbot_common::traits::api::OscApi;
 use maowbot_common::traits::osc_toggle_traits::OscToggleRepository;
+use maowbot_core::services::drip_pack;
+use maowbot_core::services::midi::{MidiService, NewMidiMapping};
+use maowbot_common::models::midi_mapping::{MidiActionKind, MidiTrigger, MidiTriggerKind};
 use std::sync::Arc;
 use chrono::Utc;
-use tracing::{info, error, debug};
+use tracing::{info, debug};
 use prost_types;
 use uuid::Uuid;
+use super::convert::to_status;
 
 pub struct OscServiceImpl {
     plugin_manager: Arc<PluginManager>,
     osc_toggle_repo: Arc<dyn OscToggleRepository + Send + Sync>,
+    midi_service: Arc<MidiService>,
 }
 
 impl OscServiceImpl {
-    pub fn new(plugin_manager: Arc<PluginManager>, osc_toggle_repo: Arc<dyn OscToggleRepository + Send + Sync>) -> Self {
+    pub fn new(
+        plugin_manager: Arc<PluginManager>,
+        osc_toggle_repo: Arc<dyn OscToggleRepository + Send + Sync>,
+        midi_service: Arc<MidiService>,
+    ) -> Self {
         Self {
             plugin_manager,
             osc_toggle_repo,
+            midi_service,
         }
     }
+
+    /// The avatar the VRChat watcher considers current, if any.
+    async fn current_avatar(&self) -> Option<maowbot_osc::vrchat::VrchatAvatarConfig> {
+        let mgr = self.plugin_manager.osc_manager.as_ref()?;
+        mgr.current_avatar_config().await
+            .map_err(|e| debug!("No current avatar for drip pack: {}", e))
+            .ok()
+            .flatten()
+    }
+}
+
+fn chat_control_to_proto(c: maowbot_common::models::osc_toggle::OscChatControl) -> OscChatControl {
+    OscChatControl {
+        name: c.name,
+        parameter_name: c.parameter_name,
+        parameter_type: c.parameter_type,
+        min_role: c.min_role,
+        min_value: c.min_value,
+        max_value: c.max_value,
+        cooldown_seconds: c.cooldown_seconds,
+        enabled: c.enabled,
+    }
+}
+
+fn midi_mapping_to_proto(m: maowbot_common::models::midi_mapping::MidiMapping) -> MidiMapping {
+    MidiMapping {
+        mapping_id: m.mapping_id.to_string(),
+        name: m.name,
+        trigger_kind: m.trigger.kind.to_string(),
+        channel: m.trigger.channel as i32,
+        number: m.trigger.number as i32,
+        action: m.action.to_string(),
+        target: m.target,
+        argument: m.argument,
+        enabled: m.enabled,
+    }
 }
 
 #[tonic::async_trait]
@@ -227,7 +273,7 @@ impl OscService for OscServiceImpl {
         let req = request.into_inner();
         info!("Batch sending {} avatar parameters", req.parameters.len());
         
-        let mut results: Vec<String> = Vec::new();
+        let _results: Vec<String> = Vec::new();
         
         let mut failed_params = Vec::new();
         let mut success_count = 0;
@@ -464,7 +510,7 @@ impl OscService for OscServiceImpl {
         }
         
         let triggers: Vec<TriggerWithRedeems> = trigger_redeems_map.into_iter()
-            .map(|(_, (trigger, redeem_names))| TriggerWithRedeems {
+            .map(|(_, (trigger, _redeem_names))| TriggerWithRedeems {
                 trigger: Some(trigger),
                 linked_redeems: vec![], // TODO: Convert redeem names to full Redeem protos
             })
@@ -516,13 +562,168 @@ impl OscService for OscServiceImpl {
         
         // For toggle state, we need to look up the toggle to get the associated redeem and user
         // TODO: This needs proper implementation
-        let toggle_id = req.toggle_id.parse::<i32>()
+        let _toggle_id = req.toggle_id.parse::<i32>()
             .map_err(|e| Status::invalid_argument(format!("Invalid toggle ID: {}", e)))?;
         
         // TODO: Implement toggle state management
         // This requires looking up the toggle and updating its state
-        return Err(Status::unimplemented("Toggle state management not yet implemented"));
-        
+        Err(Status::unimplemented("Toggle state management not yet implemented"))
+    }
+    async fn export_drip_pack(&self, request: Request<ExportDripPackRequest>) -> Result<Response<ExportDripPackResponse>, Status> {
+        let req = request.into_inner();
+        if req.name.trim().is_empty() {
+            return Err(Status::invalid_argument("Pack name is required"));
+        }
+        info!("Exporting drip pack '{}'", req.name);
+
+        let avatar_name = self.current_avatar().await.map(|a| a.name);
+        let mut pack = drip_pack::export_pack(
+            req.name.trim(),
+            avatar_name,
+            &*self.plugin_manager.redeem_service.redeem_repo,
+            &*self.osc_toggle_repo,
+            &*self.plugin_manager.credentials_repo,
+        ).await.map_err(to_status)?;
+        pack.author = Some(req.author).filter(|a| !a.is_empty());
+        pack.description = Some(req.description).filter(|d| !d.is_empty());
+
+        let pack_json = serde_json::to_string_pretty(&pack)
+            .map_err(|e| Status::internal(format!("Failed to serialize pack: {}", e)))?;
+        Ok(Response::new(ExportDripPackResponse {
+            pack_json,
+            toggle_count: pack.toggles.len() as i32,
+        }))
+    }
+    async fn preview_drip_pack(&self, request: Request<PreviewDripPackRequest>) -> Result<Response<PreviewDripPackResponse>, Status> {
+        let pack = drip_pack::parse_pack(&request.into_inner().pack_json).map_err(to_status)?;
+        let avatar = self.current_avatar().await;
+        let parameters = avatar.as_ref().map(drip_pack::avatar_parameters).unwrap_or_default();
+        let matches = drip_pack::match_parameters(&pack, &parameters);
+
+        Ok(Response::new(PreviewDripPackResponse {
+            pack_name: pack.name,
+            author: pack.author.unwrap_or_default(),
+            description: pack.description.unwrap_or_default(),
+            toggle_count: pack.toggles.len() as i32,
+            avatar_id: avatar.as_ref().map(|a| a.id.clone()).unwrap_or_default(),
+            avatar_name: avatar.map(|a| a.name).unwrap_or_default(),
+            matches: matches.into_iter().map(|m| DripPackParameterMatch {
+                pack_parameter: m.pack_parameter,
+                parameter_type: m.parameter_type,
+                avatar_parameter: m.avatar_parameter.unwrap_or_default(),
+                avatar_type: m.avatar_type.unwrap_or_default(),
+                match_kind: m.kind.to_string(),
+            }).collect(),
+            avatar_parameters: parameters.into_iter().map(|p| DripPackAvatarParameter {
+                name: p.name,
+                parameter_type: p.parameter_type,
+            }).collect(),
+        }))
+    }
+    async fn import_drip_pack(&self, request: Request<ImportDripPackRequest>) -> Result<Response<ImportDripPackResponse>, Status> {
+        let req = request.into_inner();
+        let pack = drip_pack::parse_pack(&req.pack_json).map_err(to_status)?;
+        info!("Importing drip pack '{}' ({} toggles)", pack.name, pack.toggles.len());
+
+        let results = drip_pack::import_pack(
+            &pack,
+            &req.parameter_mapping,
+            &*self.plugin_manager.redeem_service.redeem_repo,
+            &*self.osc_toggle_repo,
+            &*self.plugin_manager.credentials_repo,
+        ).await.map_err(to_status)?;
+
+        Ok(Response::new(ImportDripPackResponse {
+            results: results.into_iter().map(|r| DripPackImportResult {
+                reward_name: r.reward_name,
+                parameter_name: r.parameter_name,
+                status: r.status.to_string(),
+                redeem_id: r.redeem_id.map(|id| id.to_string()).unwrap_or_default(),
+                trigger_id: r.trigger_id.unwrap_or(0),
+                created_redeem: r.created_redeem,
+                detail: r.detail,
+            }).collect(),
+        }))
+    }
+    async fn list_midi_mappings(&self, _request: Request<ListMidiMappingsRequest>) -> Result<Response<ListMidiMappingsResponse>, Status> {
+        let mappings = self.midi_service.list_mappings().await.map_err(to_status)?;
+        Ok(Response::new(ListMidiMappingsResponse {
+            mappings: mappings.into_iter().map(midi_mapping_to_proto).collect(),
+            device: self.midi_service.connected_device().unwrap_or_default(),
+        }))
+    }
+    async fn create_midi_mapping(&self, request: Request<CreateMidiMappingRequest>) -> Result<Response<CreateMidiMappingResponse>, Status> {
+        let req = request.into_inner();
+        let action: MidiActionKind = req.action.parse().map_err(to_status)?;
+        let trigger = if req.learn {
+            let timeout = if req.learn_timeout_seconds > 0 { req.learn_timeout_seconds as u64 } else { 15 };
+            info!("Learning MIDI mapping '{}' for {}s", req.name, timeout);
+            self.midi_service.learn(std::time::Duration::from_secs(timeout.min(120))).await.map_err(to_status)?
+        } else {
+            let kind: MidiTriggerKind = req.trigger_kind.parse().map_err(to_status)?;
+            if !(1..=16).contains(&req.channel) || !(0..=127).contains(&req.number) {
+                return Err(Status::invalid_argument("MIDI channels are 1-16 and note/controller numbers 0-127"));
+            }
+            MidiTrigger { kind, channel: req.channel as u8, number: req.number as u8 }
+        };
+        let mapping = self.midi_service.add_mapping(NewMidiMapping {
+            name: req.name,
+            trigger,
+            action,
+            target: req.target,
+            argument: req.argument,
+        }).await.map_err(to_status)?;
+        Ok(Response::new(CreateMidiMappingResponse {
+            mapping: Some(midi_mapping_to_proto(mapping)),
+        }))
+    }
+    async fn set_midi_mapping_enabled(&self, request: Request<SetMidiMappingEnabledRequest>) -> Result<Response<SetMidiMappingEnabledResponse>, Status> {
+        let req = request.into_inner();
+        let mapping = self.midi_service.set_enabled(&req.name, req.enabled).await.map_err(to_status)?;
+        Ok(Response::new(SetMidiMappingEnabledResponse {
+            mapping: Some(midi_mapping_to_proto(mapping)),
+        }))
+    }
+    async fn delete_midi_mapping(&self, request: Request<DeleteMidiMappingRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        self.midi_service.delete_mapping(&req.name).await.map_err(to_status)?;
+        info!("Deleted MIDI mapping '{}'", req.name);
+        Ok(Response::new(()))
+    }
+    async fn list_osc_chat_controls(&self, _request: Request<ListOscChatControlsRequest>) -> Result<Response<ListOscChatControlsResponse>, Status> {
+        let controls = self.osc_toggle_repo.list_chat_controls().await.map_err(to_status)?;
+        Ok(Response::new(ListOscChatControlsResponse {
+            controls: controls.into_iter().map(chat_control_to_proto).collect(),
+        }))
+    }
+    async fn set_osc_chat_control(&self, request: Request<SetOscChatControlRequest>) -> Result<Response<SetOscChatControlResponse>, Status> {
+        let c = request.into_inner().control.ok_or_else(|| Status::invalid_argument("Control is required"))?;
+        let control = maowbot_common::models::osc_toggle::OscChatControl {
+            id: 0,
+            name: c.name.trim().to_lowercase(),
+            parameter_name: c.parameter_name.trim().to_string(),
+            parameter_type: c.parameter_type.to_lowercase(),
+            min_role: c.min_role.to_lowercase(),
+            min_value: c.min_value,
+            max_value: c.max_value,
+            cooldown_seconds: c.cooldown_seconds,
+            enabled: c.enabled,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        control.validate().map_err(Status::invalid_argument)?;
+        let saved = self.osc_toggle_repo.upsert_chat_control(control).await.map_err(to_status)?;
+        info!("Saved OSC chat control '{}' for {}", saved.name, saved.parameter_name);
+        Ok(Response::new(SetOscChatControlResponse {
+            control: Some(chat_control_to_proto(saved)),
+        }))
+    }
+    async fn delete_osc_chat_control(&self, request: Request<DeleteOscChatControlRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        if !self.osc_toggle_repo.delete_chat_control(&req.name).await.map_err(to_status)? {
+            return Err(Status::not_found(format!("No chat control named '{}'", req.name)));
+        }
+        info!("Deleted OSC chat control '{}'", req.name);
         Ok(Response::new(()))
     }
     async fn send_raw_osc(&self, request: Request<SendRawOscRequest>) -> Result<Response<()>, Status> {
@@ -0,0 +1,138 @@
+// Pagination, sorting and read-mask handling shared by the list RPCs.
+//
+// Conventions (see `PageRequest` in common.proto):
+// - `page_size` 0 means DEFAULT_PAGE_SIZE; larger than MAX_PAGE_SIZE is clamped.
+// - `page_token` is opaque to clients: pass back `next_page_token` from the
+//   previous response with the same filters and sort. Empty means the first page.
+// - `next_page_token` is empty on the last page; `total_count` counts every match.
+// - An empty `read_mask` returns every field; otherwise only the listed top-level
+//   fields are filled in (identifiers always are).
+
+use prost_types::FieldMask;
+use tonic::Status;
+use maowbot_proto::maowbot::common::{PageRequest, PageResponse};
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 500;
+
+const TOKEN_PREFIX: &str = "o";
+
+/// The window a list request asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl Page {
+    pub fn from_request(page: Option<&PageRequest>) -> Result<Page, Status> {
+        let Some(page) = page else {
+            return Ok(Page { offset: 0, size: DEFAULT_PAGE_SIZE });
+        };
+        if page.page_size < 0 {
+            return Err(Status::invalid_argument("page_size must not be negative"));
+        }
+        let size = match page.page_size as usize {
+            0 => DEFAULT_PAGE_SIZE,
+            n => n.min(MAX_PAGE_SIZE),
+        };
+        Ok(Page { offset: decode_token(&page.page_token)?, size })
+    }
+
+    /// The page response after returning `returned` of `total` items from this window.
+    pub fn response(&self, returned: usize, total: usize) -> PageResponse {
+        let next = self.offset + returned;
+        PageResponse {
+            next_page_token: if returned > 0 && next < total { encode_token(next) } else { String::new() },
+            total_count: total.min(i32::MAX as usize) as i32,
+        }
+    }
+
+    /// Cuts this window out of the full, already sorted list.
+    pub fn slice<T>(&self, items: Vec<T>) -> (Vec<T>, PageResponse) {
+        let total = items.len();
+        let items: Vec<T> = items.into_iter().skip(self.offset).take(self.size).collect();
+        let page = self.response(items.len(), total);
+        (items, page)
+    }
+}
+
+fn encode_token(offset: usize) -> String {
+    format!("{}{}", TOKEN_PREFIX, offset)
+}
+
+fn decode_token(token: &str) -> Result<usize, Status> {
+    if token.is_empty() {
+        return Ok(0);
+    }
+    token.strip_prefix(TOKEN_PREFIX)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| Status::invalid_argument("Invalid page_token"))
+}
+
+/// Which fields a list response should fill in.
+#[derive(Debug, Clone, Default)]
+pub struct ReadMask(Option<Vec<String>>);
+
+impl ReadMask {
+    /// Checks the mask's paths against the fields the item type has.
+    pub fn parse(mask: Option<&FieldMask>, fields: &[&str]) -> Result<ReadMask, Status> {
+        let Some(mask) = mask.filter(|m| !m.paths.is_empty()) else {
+            return Ok(ReadMask(None));
+        };
+        for path in &mask.paths {
+            if !fields.contains(&path.as_str()) {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown read_mask field '{}' (expected one of: {})", path, fields.join(", ")
+                )));
+            }
+        }
+        Ok(ReadMask(Some(mask.paths.clone())))
+    }
+
+    pub fn includes(&self, field: &str) -> bool {
+        match &self.0 {
+            Some(paths) => paths.iter().any(|p| p == field),
+            None => true,
+        }
+    }
+}
+
+/// Checks `order_by` against the sortable fields; empty picks the first.
+pub fn sort_field<'a>(order_by: &str, fields: &[&'a str]) -> Result<&'a str, Status> {
+    if order_by.is_empty() {
+        return Ok(fields[0]);
+    }
+    fields.iter()
+        .find(|f| f.eq_ignore_ascii_case(order_by))
+        .copied()
+        .ok_or_else(|| Status::invalid_argument(format!(
+            "Cannot sort by '{}' (expected one of: {})", order_by, fields.join(", ")
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages_follow_their_tokens_to_the_end() {
+        let items: Vec<u32> = (0..7).collect();
+        let mut request = PageRequest { page_size: 3, page_token: String::new() };
+        let mut seen = Vec::new();
+        loop {
+            let page = Page::from_request(Some(&request)).unwrap();
+            let (items, response) = page.slice(items.clone());
+            assert_eq!(response.total_count, 7);
+            seen.extend(items);
+            if response.next_page_token.is_empty() {
+                break;
+            }
+            request.page_token = response.next_page_token;
+        }
+        assert_eq!(seen, (0..7).collect::<Vec<_>>());
+
+        assert!(Page::from_request(Some(&PageRequest { page_size: 1, page_token: "bogus".into() })).is_err());
+        assert_eq!(Page::from_request(Some(&PageRequest { page_size: 10_000, page_token: String::new() })).unwrap().size, MAX_PAGE_SIZE);
+    }
+}
@@ -4,7 +4,7 @@ use maowbot_proto::maowbot::services::{
     *,
 };
 use maowbot_proto::maowbot::common::{Platform, PlatformConfig as ProtoPlatformConfig};
-use maowbot_core::platforms::manager::PlatformManager;
+use maowbot_core::platforms::manager::{ConnectionSnapshot, ConnectionState, PlatformManager};
 use maowbot_common::{
     models::platform::PlatformConfig as PlatformConfigModel,
     traits::repository_traits::PlatformConfigRepository as PlatformConfigRepositoryTrait,
@@ -13,7 +13,7 @@ use std::sync::Arc;
 use std::collections::HashMap;
 use uuid::Uuid;
 use chrono::Utc;
-use tracing::{info, error, debug};
+use tracing::{info, debug};
 use prost_types;
 
 pub struct PlatformServiceImpl {
@@ -32,6 +32,56 @@ impl PlatformServiceImpl {
         }
     }
     
+    /// Connection supervisor state as a RuntimeStatus.
+    fn connection_status(snapshot: &ConnectionSnapshot) -> RuntimeStatus {
+        let (state, message) = match snapshot.state {
+            ConnectionState::Connected => (runtime_status::State::Running, "Connected".to_string()),
+            ConnectionState::Degraded => (
+                runtime_status::State::Running,
+                format!("Degraded: reconnected after {} attempt(s)", snapshot.attempt),
+            ),
+            ConnectionState::Reconnecting => (
+                runtime_status::State::Reconnecting,
+                format!(
+                    "Reconnecting (attempt {}): {}",
+                    snapshot.attempt,
+                    snapshot.last_error.as_deref().unwrap_or("connection lost")
+                ),
+            ),
+            ConnectionState::Failed => (
+                runtime_status::State::Error,
+                format!(
+                    "Gave up reconnecting: {}",
+                    snapshot.last_error.as_deref().unwrap_or("connection lost")
+                ),
+            ),
+            ConnectionState::Stopped => (runtime_status::State::Stopped, "Not running".to_string()),
+        };
+        RuntimeStatus {
+            state: state as i32,
+            message,
+            since: Some(prost_types::Timestamp {
+                seconds: snapshot.changed_at.timestamp(),
+                nanos: snapshot.changed_at.timestamp_subsec_nanos() as i32,
+            }),
+        }
+    }
+
+    /// Connection details surfaced through `RuntimeInfo.platform_specific`.
+    fn connection_fields(snapshot: &ConnectionSnapshot) -> HashMap<String, String> {
+        let mut fields = HashMap::new();
+        fields.insert("connection_state".to_string(), snapshot.state.to_string());
+        fields.insert("reconnect_attempts".to_string(), snapshot.attempt.to_string());
+        fields.insert("account".to_string(), snapshot.account_name.clone());
+        if let Some(err) = &snapshot.last_error {
+            fields.insert("last_error".to_string(), err.clone());
+        }
+        if let Some(next) = snapshot.next_retry_at {
+            fields.insert("next_retry_at".to_string(), next.to_rfc3339());
+        }
+        fields
+    }
+
     fn platform_config_to_proto(config: &PlatformConfigModel) -> ProtoPlatformConfig {
         ProtoPlatformConfig {
             platform_config_id: config.platform_config_id.to_string(),
@@ -59,6 +109,7 @@ impl PlatformServiceImpl {
             "discord" => Platform::Discord as i32,
             "vrchat" => Platform::Vrchat as i32,
             "vrchat-pipeline" => Platform::VrchatPipeline as i32,
+            "kick" => Platform::Kick as i32,
             "twitch-helix" => Platform::TwitchHelix as i32,
             _ => Platform::Unknown as i32,
         }
@@ -71,6 +122,7 @@ impl PlatformServiceImpl {
             Ok(Platform::Discord) => Ok("discord".to_string()),
             Ok(Platform::Vrchat) => Ok("vrchat".to_string()),
             Ok(Platform::VrchatPipeline) => Ok("vrchat-pipeline".to_string()),
+            Ok(Platform::Kick) => Ok("kick".to_string()),
             Ok(Platform::TwitchHelix) => Ok("twitch".to_string()),
             _ => Err(Status::invalid_argument("Invalid platform")),
         }
@@ -319,33 +371,46 @@ impl PlatformService for PlatformServiceImpl {
         let platform = &req.platform;
         let account_name = &req.account_name;
         
-        // Check if runtime is active
+        // Runtimes are keyed by user_id; the supervisor knows which account each belongs to
         let pm = &self.platform_manager;
+        let snapshot = pm.connection_supervisor
+            .snapshots()
+            .into_iter()
+            .find(|s| s.platform == *platform && s.account_name.eq_ignore_ascii_case(account_name));
         let runtimes_guard = pm.active_runtimes.lock().await;
-        let is_active = runtimes_guard.contains_key(&(platform.to_string(), account_name.to_string()));
+        let handle = runtimes_guard.iter()
+            .find(|((p, id), _)| {
+                p == platform
+                    && (id == account_name || snapshot.as_ref().map(|s| &s.user_id == id).unwrap_or(false))
+            })
+            .map(|(_, handle)| handle);
+        let started_at = handle.map(|h| h.started_at);
         drop(runtimes_guard);
-        
-        let status = if is_active {
-            RuntimeStatus {
+
+        let status = match (&snapshot, started_at) {
+            (Some(snapshot), _) => Self::connection_status(snapshot),
+            (None, Some(_)) => RuntimeStatus {
                 state: runtime_status::State::Running as i32,
                 message: "Running".to_string(),
-                since: None, // TODO: Track start time
-            }
-        } else {
-            RuntimeStatus {
+                since: None,
+            },
+            (None, None) => RuntimeStatus {
                 state: runtime_status::State::Stopped as i32,
                 message: "Not running".to_string(),
                 since: None,
-            }
+            },
         };
-        
+
         Ok(Response::new(GetPlatformRuntimeStatusResponse {
             info: Some(RuntimeInfo {
                 runtime_id: format!("{}-{}", platform, account_name),
                 platform: platform.to_string(),
                 account_name: account_name.to_string(),
-                    started_at: None, // TODO: Track start time
-                uptime_seconds: 0, // TODO: Calculate uptime
+                started_at: started_at.map(|t| prost_types::Timestamp {
+                    seconds: t.timestamp(),
+                    nanos: t.timestamp_subsec_nanos() as i32,
+                }),
+                uptime_seconds: started_at.map(|t| (Utc::now() - t).num_seconds()).unwrap_or(0),
                 stats: Some(RuntimeStatistics {
                     messages_sent: 0,
                     messages_received: 0,
@@ -353,7 +418,7 @@ impl PlatformService for PlatformServiceImpl {
                     errors_count: 0,
                     last_activity: None,
                 }),
-                platform_specific: HashMap::new(),
+                platform_specific: snapshot.as_ref().map(Self::connection_fields).unwrap_or_default(),
             }),
             status: Some(status),
         }))
@@ -398,11 +463,41 @@ impl PlatformService for PlatformServiceImpl {
                     errors_count: 0,
                     last_activity: None,
                 }),
-                platform_specific: HashMap::new(),
+                platform_specific: pm.connection_supervisor
+                    .snapshot(platform, account_name)
+                    .map(|s| Self::connection_fields(&s))
+                    .unwrap_or_default(),
             });
         }
         
         drop(runtimes_guard);
+
+        // Supervised runtimes waiting to reconnect (or given up) have no handle but still belong in the list
+        for snapshot in pm.connection_supervisor.snapshots() {
+            if !matches!(snapshot.state, ConnectionState::Reconnecting | ConnectionState::Failed) {
+                continue;
+            }
+            if !req.platforms.is_empty()
+                && !req.platforms.contains(&Self::platform_str_to_proto(&snapshot.platform))
+            {
+                continue;
+            }
+            runtime_infos.push(RuntimeInfo {
+                runtime_id: format!("{}-{}", snapshot.platform, snapshot.user_id),
+                platform: snapshot.platform.clone(),
+                account_name: snapshot.user_id.clone(),
+                started_at: None,
+                uptime_seconds: 0,
+                stats: Some(RuntimeStatistics {
+                    messages_sent: 0,
+                    messages_received: 0,
+                    events_processed: 0,
+                    errors_count: 0,
+                    last_activity: None,
+                }),
+                platform_specific: Self::connection_fields(&snapshot),
+            });
+        }
         
         let mut runtime_counts = HashMap::new();
         for info in &runtime_infos {
@@ -522,6 +617,32 @@ impl PlatformService for PlatformServiceImpl {
                 ]);
                 (caps, scopes, limits)
             }
+            Platform::Kick => {
+                let caps = vec![
+                    Capability {
+                        name: "chat".to_string(),
+                        description: "Read chat over websocket, send via the public API".to_string(),
+                        requires_auth: true,
+                        required_roles: vec![],
+                    },
+                    Capability {
+                        name: "events".to_string(),
+                        description: "Follow and subscription events where Kick publishes them".to_string(),
+                        requires_auth: false,
+                        required_roles: vec![],
+                    },
+                ];
+                let scopes = vec![
+                    "user:read".to_string(),
+                    "channel:read".to_string(),
+                    "chat:write".to_string(),
+                    "events:subscribe".to_string(),
+                ];
+                let limits = HashMap::from([
+                    ("note".to_string(), "Chat is read from Kick's public chat websocket".to_string()),
+                ]);
+                (caps, scopes, limits)
+            }
             _ => {
                 let caps = vec![];
                 let scopes = vec![];
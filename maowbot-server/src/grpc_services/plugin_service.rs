@@ -5,21 +5,91 @@ use maowbot_proto::maowbot::services::{
 };
 use maowbot_proto::maowbot::common::Plugin as ProtoPlugin;
 use maowbot_core::plugins::manager::PluginManager;
+use maowbot_core::services::chat_load;
+use maowbot_common::models::workspace::{channel_key, channel_platform};
 use maowbot_common::traits::api::PluginApi;
 use std::sync::Arc;
 use std::collections::HashMap;
-use tracing::{info, error, debug};
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tracing::{info, debug};
 use prost_types;
 use uuid;
 use chrono::Utc;
+use serde_json::json;
+
+use crate::authz::AuditNote;
+use crate::context::ServerContext;
+use crate::diagnostics::{self, CheckStatus};
 
 pub struct PluginServiceImpl {
     plugin_manager: Arc<PluginManager>,
+    ctx: Arc<ServerContext>,
 }
 
 impl PluginServiceImpl {
-    pub fn new(plugin_manager: Arc<PluginManager>) -> Self {
-        Self { plugin_manager }
+    pub fn new(plugin_manager: Arc<PluginManager>, ctx: Arc<ServerContext>) -> Self {
+        Self { plugin_manager, ctx }
+    }
+
+    /// Whether the plugin is enabled, for the audit log; None if it isn't known.
+    fn plugin_enabled(&self, name: &str) -> Option<bool> {
+        self.plugin_manager.get_plugin_records().iter()
+            .find(|r| r.name == name)
+            .map(|r| r.enabled)
+    }
+}
+
+fn latency_to_proto(l: chat_load::LatencySummary) -> ChatLoadLatency {
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    ChatLoadLatency {
+        count: l.count as i64,
+        mean_ms: ms(l.mean),
+        p50_ms: ms(l.p50),
+        p90_ms: ms(l.p90),
+        p99_ms: ms(l.p99),
+        max_ms: ms(l.max),
+    }
+}
+
+fn load_progress_to_proto(p: chat_load::ChatLoadProgress) -> ChatLoadTestUpdate {
+    ChatLoadTestUpdate {
+        update: Some(chat_load_test_update::Update::Progress(ChatLoadProgress {
+            elapsed_ms: p.elapsed.as_millis() as i64,
+            sent: p.sent as i64,
+            delivered: p.delivered as i64,
+            errors: p.errors as i64,
+            in_flight: p.in_flight as i64,
+        })),
+    }
+}
+
+fn load_report_to_proto(r: chat_load::ChatLoadReport) -> ChatLoadTestUpdate {
+    ChatLoadTestUpdate {
+        update: Some(chat_load_test_update::Update::Report(ChatLoadReport {
+            sent: r.sent as i64,
+            delivered: r.delivered as i64,
+            dropped: r.dropped as i64,
+            errors: r.errors as i64,
+            first_error: r.first_error.unwrap_or_default(),
+            peak_in_flight: r.peak_in_flight as i64,
+            elapsed_ms: r.elapsed.as_millis() as i64,
+            achieved_rate: r.achieved_rate,
+            ingest: Some(latency_to_proto(r.ingest)),
+            end_to_end: Some(latency_to_proto(r.end_to_end)),
+        })),
+    }
+}
+
+fn diagnostic_status(status: CheckStatus) -> DiagnosticStatus {
+    match status {
+        CheckStatus::Ok => DiagnosticStatus::Ok,
+        CheckStatus::Skipped => DiagnosticStatus::Skipped,
+        CheckStatus::Warning => DiagnosticStatus::Warning,
+        CheckStatus::Failed => DiagnosticStatus::Failed,
     }
 }
 
@@ -71,10 +141,10 @@ impl GrpcPluginService for PluginServiceImpl {
                     is_connected: connected_info.is_some(),
                     version: String::new(), // Not stored in PluginRecord
                     capabilities: connected_info.map(|i| i.capabilities.iter().map(|c| format!("{:?}", c)).collect()).unwrap_or_default(),
-                    connected_at: connected_info.and_then(|_| Some(prost_types::Timestamp {
+                    connected_at: connected_info.map(|_| prost_types::Timestamp {
                         seconds: Utc::now().timestamp(),
                         nanos: 0,
-                    })),
+                    }),
                     metadata: HashMap::new(),
                 }),
                 status: Some(PluginStatus {
@@ -139,10 +209,10 @@ impl GrpcPluginService for PluginServiceImpl {
                 is_connected: connected_info.is_some(),
                 version: String::new(),
                 capabilities: connected_info.map(|i| i.capabilities.iter().map(|c| format!("{:?}", c)).collect()).unwrap_or_default(),
-                connected_at: connected_info.and_then(|_| Some(prost_types::Timestamp {
+                connected_at: connected_info.map(|_| prost_types::Timestamp {
                     seconds: Utc::now().timestamp(),
                     nanos: 0,
-                })),
+                }),
                 metadata: HashMap::new(),
             }),
             status: Some(PluginStatus {
@@ -161,7 +231,7 @@ impl GrpcPluginService for PluginServiceImpl {
             }),
         };
         
-        let mut config = HashMap::new();
+        let config = HashMap::new();
         if req.include_config {
             // TODO: Load plugin config from storage
         }
@@ -176,12 +246,15 @@ impl GrpcPluginService for PluginServiceImpl {
         &self,
         request: Request<EnablePluginRequest>,
     ) -> Result<Response<EnablePluginResponse>, Status> {
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Enabling plugin: {}", req.plugin_name);
         
         // Enable the plugin
+        let was_enabled = self.plugin_enabled(&req.plugin_name);
         self.plugin_manager.toggle_plugin(&req.plugin_name, true).await
             .map_err(|e| Status::internal(format!("Failed to enable plugin: {}", e)))?;
+        audit.change(format!("plugin:{}", req.plugin_name), was_enabled.map(|e| json!({ "enabled": e })), Some(json!({ "enabled": true })));
         
         // Get updated plugin info
         let plugin_records = self.plugin_manager.get_plugin_records();
@@ -225,12 +298,15 @@ impl GrpcPluginService for PluginServiceImpl {
         &self,
         request: Request<DisablePluginRequest>,
     ) -> Result<Response<()>, Status> {
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Disabling plugin: {}", req.plugin_name);
         
         // Disable the plugin
+        let was_enabled = self.plugin_enabled(&req.plugin_name);
         self.plugin_manager.toggle_plugin(&req.plugin_name, false).await
             .map_err(|e| Status::internal(format!("Failed to disable plugin: {}", e)))?;
+        audit.change(format!("plugin:{}", req.plugin_name), was_enabled.map(|e| json!({ "enabled": e })), Some(json!({ "enabled": false })));
         
         Ok(Response::new(()))
     }
@@ -239,12 +315,15 @@ impl GrpcPluginService for PluginServiceImpl {
         &self,
         request: Request<RemovePluginRequest>,
     ) -> Result<Response<()>, Status> {
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Removing plugin: {}", req.plugin_name);
         
         // Remove the plugin
+        let was_enabled = self.plugin_enabled(&req.plugin_name);
         self.plugin_manager.remove_plugin(&req.plugin_name).await
             .map_err(|e| Status::internal(format!("Failed to remove plugin: {}", e)))?;
+        audit.change(format!("plugin:{}", req.plugin_name), was_enabled.map(|e| json!({ "enabled": e })), None);
         
         // TODO: If remove_config or remove_data are true, clean up those as well
         
@@ -436,7 +515,7 @@ impl GrpcPluginService for PluginServiceImpl {
         
         // Find the plugin record
         let plugin_records = self.plugin_manager.get_plugin_records();
-        let record = plugin_records.iter()
+        let _record = plugin_records.iter()
             .find(|r| r.name == req.plugin_name)
             .ok_or_else(|| Status::not_found(format!("Plugin '{}' not found", req.plugin_name)))?;
         
@@ -529,17 +608,25 @@ impl GrpcPluginService for PluginServiceImpl {
         let plugin_records = self.plugin_manager.get_plugin_records();
         
         // Count active accounts
-        let active_accounts = status_data.account_statuses.iter()
+        let _active_accounts = status_data.account_statuses.iter()
             .filter(|a| a.is_connected)
             .count() as i32;
         
+        let (total_events, event_counts) = match &self.plugin_manager.event_bus {
+            Some(bus) => (
+                bus.metrics.total_events() as i64,
+                bus.metrics.counts_by_type().into_iter().map(|(k, v)| (k, v as i64)).collect(),
+            ),
+            None => (0, HashMap::new()),
+        };
+        
         let system_metrics = SystemMetrics {
             cpu_usage_percent: 0.0, // TODO: Get actual CPU usage
             memory_used_bytes: 0, // TODO: Get actual memory usage
             memory_total_bytes: 0, // TODO: Get total memory
-            total_messages_processed: 0, // TODO: Get message count
-            messages_per_second: 0.0, // TODO: Calculate message rate
-            event_counts: HashMap::new(), // TODO: Track event counts
+            total_messages_processed: total_events,
+            messages_per_second: 0.0, // Clients derive rates from GetRuntimeMetrics samples
+            event_counts,
         };
         
         Ok(Response::new(GetSystemStatusResponse {
@@ -550,4 +637,162 @@ impl GrpcPluginService for PluginServiceImpl {
             warnings: vec![],
         }))
     }
-}
\ No newline at end of file
+    
+    async fn get_runtime_metrics(
+        &self,
+        request: Request<GetRuntimeMetricsRequest>,
+    ) -> Result<Response<GetRuntimeMetricsResponse>, Status> {
+        let req = request.into_inner();
+        
+        let status_data = self.plugin_manager.status().await;
+        let connections = status_data.account_statuses.iter()
+            .map(|a| ConnectionMetrics {
+                platform: a.platform.clone(),
+                account: a.account_name.clone(),
+                connected: a.is_connected,
+            })
+            .collect();
+        
+        let (events_total, event_counts) = match &self.plugin_manager.event_bus {
+            Some(bus) => {
+                let counts = if req.include_event_breakdown {
+                    bus.metrics.counts_by_type().into_iter().map(|(k, v)| (k, v as i64)).collect()
+                } else {
+                    HashMap::new()
+                };
+                (bus.metrics.total_events() as i64, counts)
+            }
+            None => (0, HashMap::new()),
+        };
+        
+        let (osc_running, osc_packets_received, osc_packets_sent) = match &self.plugin_manager.osc_manager {
+            Some(osc) => {
+                let running = osc.get_status().await.map(|s| s.is_running).unwrap_or(false);
+                let (received, sent) = osc.counters.snapshot();
+                (running, received as i64, sent as i64)
+            }
+            None => (false, 0, 0),
+        };
+        
+        let outbound = self.plugin_manager.platform_manager.outbound_guard().map(|guard| {
+            let counters = guard.counters();
+            OutboundMetrics {
+                sent: counters.sent as i64,
+                duplicates_dropped: counters.duplicates_dropped as i64,
+                rate_limited: counters.rate_limited as i64,
+                own_messages_ignored: counters.own_messages_ignored as i64,
+            }
+        });
+        
+        Ok(Response::new(GetRuntimeMetricsResponse {
+            uptime_seconds: status_data.uptime_seconds as i64,
+            sampled_at_ms: Utc::now().timestamp_millis(),
+            events_total,
+            event_counts,
+            osc_running,
+            osc_packets_received,
+            osc_packets_sent,
+            connections,
+            outbound,
+        }))
+    }
+    
+    async fn run_diagnostics(
+        &self,
+        request: Request<RunDiagnosticsRequest>,
+    ) -> Result<Response<RunDiagnosticsResponse>, Status> {
+        let req = request.into_inner();
+        if let Some(unknown) = req.categories.iter().find(|c| !diagnostics::CATEGORIES.contains(&c.to_lowercase().as_str())) {
+            return Err(Status::invalid_argument(format!(
+                "Unknown check '{}'; use one of: {}", unknown, diagnostics::CATEGORIES.join(", ")
+            )));
+        }
+        
+        let report = diagnostics::run_diagnostics(&self.ctx, &req.categories).await;
+        let overall = diagnostic_status(report.overall());
+        let checks = report.checks.into_iter()
+            .map(|c| DiagnosticCheck {
+                category: c.category.to_string(),
+                name: c.name,
+                status: diagnostic_status(c.status) as i32,
+                message: c.message,
+                latency_ms: c.latency.map(|l| l.as_millis() as i64).unwrap_or(0),
+            })
+            .collect();
+        
+        Ok(Response::new(RunDiagnosticsResponse {
+            checks,
+            overall: overall as i32,
+            started_at: Some(prost_types::Timestamp {
+                seconds: report.started_at.timestamp(),
+                nanos: report.started_at.timestamp_subsec_nanos() as i32,
+            }),
+            duration_ms: report.duration.as_millis() as i64,
+        }))
+    }
+    
+    type RunChatLoadTestStream = Pin<Box<dyn Stream<Item = Result<ChatLoadTestUpdate, Status>> + Send>>;
+    
+    async fn run_chat_load_test(
+        &self,
+        request: Request<RunChatLoadTestRequest>,
+    ) -> Result<Response<Self::RunChatLoadTestStream>, Status> {
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let defaults = chat_load::ChatLoadConfig::default();
+        let or_default = |value: u32, default: u32| if value == 0 { default } else { value };
+        let config = chat_load::ChatLoadConfig {
+            platform: if req.platform.is_empty() { defaults.platform } else { req.platform },
+            channel: if req.channel.is_empty() { defaults.channel } else { req.channel },
+            rate_per_sec: or_default(req.rate_per_sec, defaults.rate_per_sec),
+            duration: Duration::from_secs(or_default(req.duration_secs, defaults.duration.as_secs() as u32) as u64),
+            users: or_default(req.users, defaults.users),
+            drain_timeout: defaults.drain_timeout,
+        };
+        config.validate().map_err(|e| Status::invalid_argument(e.to_string()))?;
+        // Generated chat must never land in a channel a broadcaster uses
+        let routed = self.ctx.workspace_router.routes().await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .iter()
+            .any(|r| r.platform == channel_platform(&config.platform) && r.channel == channel_key(&config.channel));
+        let known = self.ctx.message_service.is_known_channel(&config.platform, &config.channel).await
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        if routed || known {
+            return Err(Status::invalid_argument(format!(
+                "{} {} has real chat; pick a channel the bot doesn't use", config.platform, config.channel
+            )));
+        }
+        audit.change(
+            "chat_load_test",
+            None,
+            Some(json!({
+                "channel": config.channel,
+                "rate_per_sec": config.rate_per_sec,
+                "duration_secs": config.duration.as_secs(),
+            })),
+        );
+        
+        let (tx, rx) = mpsc::channel(16);
+        let (progress_tx, mut progress_rx) = mpsc::channel(4);
+        let message_service = self.ctx.message_service.clone();
+        let event_bus = self.ctx.event_bus.clone();
+        let forward = tx.clone();
+        tokio::spawn(async move {
+            while let Some(progress) = progress_rx.recv().await {
+                if forward.send(Ok(load_progress_to_proto(progress))).await.is_err() {
+                    break;
+                }
+            }
+        });
+        tokio::spawn(async move {
+            // Runs to the end even if the client goes away, so the report lands in the log
+            let result = chat_load::run_chat_load(message_service, event_bus, config, Some(progress_tx)).await;
+            let update = result
+                .map(load_report_to_proto)
+                .map_err(|e| Status::internal(e.to_string()));
+            let _ = tx.send(update).await;
+        });
+        
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
@@ -0,0 +1,107 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    protection_service_server::ProtectionService,
+    CapturedMessage, ProtectionIncident,
+    GetProtectionStatusRequest, GetProtectionStatusResponse,
+    SetShieldModeRequest, SetShieldModeResponse,
+    StartLockdownRequest, EndLockdownRequest, IncidentResponse,
+    ListIncidentsRequest, ListIncidentsResponse, GetIncidentRequest,
+};
+use maowbot_common::models::protection as model;
+use maowbot_core::services::twitch::protection_service::ProtectionService as Protection;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::authz::Caller;
+use super::convert::{to_timestamp, to_status};
+
+const DEFAULT_LIST_LIMIT: i64 = 20;
+
+pub struct ProtectionServiceImpl {
+    protection: Arc<Protection>,
+}
+
+impl ProtectionServiceImpl {
+    pub fn new(protection: Arc<Protection>) -> Self {
+        Self { protection }
+    }
+}
+
+fn caller_name<T>(request: &Request<T>) -> String {
+    request.extensions().get::<Caller>()
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "console".to_string())
+}
+
+fn incident_to_proto(i: model::ProtectionIncident) -> ProtectionIncident {
+    ProtectionIncident {
+        incident_id: i.incident_id.to_string(),
+        channel: i.channel,
+        trigger: i.trigger.to_string(),
+        details: i.details,
+        actions: i.actions,
+        messages: i.messages.into_iter().map(|m| CapturedMessage {
+            platform_user_id: m.platform_user_id,
+            username: m.username,
+            text: m.text,
+            sent_at: Some(to_timestamp(m.sent_at)),
+        }).collect(),
+        started_at: Some(to_timestamp(i.started_at)),
+        ended_at: i.ended_at.map(to_timestamp),
+        ended_by: i.ended_by.unwrap_or_default(),
+    }
+}
+
+#[tonic::async_trait]
+impl ProtectionService for ProtectionServiceImpl {
+    async fn get_protection_status(&self, _request: Request<GetProtectionStatusRequest>) -> Result<Response<GetProtectionStatusResponse>, Status> {
+        let status = self.protection.status().await;
+        Ok(Response::new(GetProtectionStatusResponse {
+            auto_enabled: status.auto_enabled,
+            shield_mode_known: status.shield_mode.is_some(),
+            shield_mode: status.shield_mode.unwrap_or(false),
+            channel: status.channel.unwrap_or_default(),
+            active_incident: status.active_incident.map(incident_to_proto),
+            window_messages: status.window_messages as i32,
+        }))
+    }
+
+    async fn set_shield_mode(&self, request: Request<SetShieldModeRequest>) -> Result<Response<SetShieldModeResponse>, Status> {
+        let caller = caller_name(&request);
+        let active = request.into_inner().active;
+        info!("Shield Mode {} requested by '{}'", if active { "on" } else { "off" }, caller);
+        self.protection.set_shield_mode(active).await.map_err(to_status)?;
+        Ok(Response::new(SetShieldModeResponse {}))
+    }
+
+    async fn start_lockdown(&self, request: Request<StartLockdownRequest>) -> Result<Response<IncidentResponse>, Status> {
+        let caller = caller_name(&request);
+        let reason = request.into_inner().reason;
+        let incident = self.protection.start_lockdown(Some(&reason), &caller).await.map_err(to_status)?;
+        Ok(Response::new(IncidentResponse { incident: Some(incident_to_proto(incident)) }))
+    }
+
+    async fn end_lockdown(&self, request: Request<EndLockdownRequest>) -> Result<Response<IncidentResponse>, Status> {
+        let caller = caller_name(&request);
+        let incident = self.protection.end_lockdown(&caller).await.map_err(to_status)?;
+        Ok(Response::new(IncidentResponse { incident: Some(incident_to_proto(incident)) }))
+    }
+
+    async fn list_incidents(&self, request: Request<ListIncidentsRequest>) -> Result<Response<ListIncidentsResponse>, Status> {
+        let req = request.into_inner();
+        let limit = if req.limit > 0 { req.limit as i64 } else { DEFAULT_LIST_LIMIT };
+        let incidents = self.protection.list_incidents(limit).await.map_err(to_status)?;
+        Ok(Response::new(ListIncidentsResponse {
+            incidents: incidents.into_iter().map(incident_to_proto).collect(),
+        }))
+    }
+
+    async fn get_incident(&self, request: Request<GetIncidentRequest>) -> Result<Response<IncidentResponse>, Status> {
+        let id = request.into_inner().incident_id;
+        let id = Uuid::parse_str(id.trim())
+            .map_err(|_| Status::invalid_argument(format!("Invalid incident id '{}'", id)))?;
+        let incident = self.protection.get_incident(id).await.map_err(to_status)?;
+        Ok(Response::new(IncidentResponse { incident: Some(incident_to_proto(incident)) }))
+    }
+}
@@ -3,38 +3,92 @@ use maowbot_proto::maowbot::services::{redeem_service_server::RedeemService, *};
 use maowbot_proto::maowbot::common;
 use maowbot_common::traits::repository_traits::{RedeemRepository, RedeemUsageRepository};
 use maowbot_core::services::twitch::redeem_service::RedeemService as CoreRedeemService;
+use maowbot_core::repositories::Repositories;
+use maowbot_core::tasks::redeem_sync::{self, RedeemChangeKind};
+use maowbot_core::services::twitch::redeem_schedule_service::RedeemScheduleService;
+use maowbot_core::services::twitch::redeem_approval_service::RedeemApprovalService;
+use maowbot_common::models::redeem_schedule::RedeemSchedule;
+use maowbot_common::models::redeem_approval::RedeemApproval as ApprovalModel;
+use crate::authz::Caller;
 use std::sync::Arc;
 use std::collections::HashMap;
 use uuid::Uuid;
 use chrono::Utc;
-use tracing::{info, error, debug};
+use tracing::{info, debug};
 use prost_types;
+use super::paging::{sort_field, Page, ReadMask};
+use super::workspace::WorkspaceResolver;
 
 pub struct RedeemServiceImpl {
-    redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
+    repos: Repositories,
     redeem_usage_repo: Arc<dyn RedeemUsageRepository + Send + Sync>,
     redeem_service: Arc<CoreRedeemService>,
+    schedule_service: Arc<RedeemScheduleService>,
+    approval_service: Arc<RedeemApprovalService>,
+    workspaces: WorkspaceResolver,
 }
 
 impl RedeemServiceImpl {
     pub fn new(
-        redeem_repo: Arc<dyn RedeemRepository + Send + Sync>,
-        redeem_usage_repo: Arc<dyn RedeemUsageRepository + Send + Sync>,
+        repos: Repositories,
         redeem_service: Arc<CoreRedeemService>,
+        schedule_service: Arc<RedeemScheduleService>,
+        approval_service: Arc<RedeemApprovalService>,
+        workspaces: WorkspaceResolver,
     ) -> Self {
         Self {
-            redeem_repo,
-            redeem_usage_repo,
+            redeem_usage_repo: repos.redeem_usage.clone(),
+            repos,
             redeem_service,
+            schedule_service,
+            approval_service,
+            workspaces,
         }
     }
+
+    /// The redeem repository for the workspace selected in `request`'s metadata.
+    async fn redeem_repo_for<T>(&self, request: &Request<T>) -> Result<Arc<dyn RedeemRepository + Send + Sync>, Status> {
+        Ok(self.repos.for_workspace(self.workspaces.resolve(request).await?).redeems)
+    }
     
+    fn schedule_to_proto(s: &RedeemSchedule, applies_now: bool) -> RedeemScheduleInfo {
+        RedeemScheduleInfo {
+            schedule_id: s.schedule_id.to_string(),
+            redeem_id: s.redeem_id.to_string(),
+            name: s.name.clone(),
+            condition: s.condition.to_string(),
+            condition_value: s.condition_value.clone(),
+            action: s.action.to_string(),
+            amount: s.amount.unwrap_or_default(),
+            priority: s.priority,
+            enabled: s.enabled,
+            applies_now,
+        }
+    }
+
+    fn approval_to_proto(a: &ApprovalModel) -> RedeemApproval {
+        let ts = |t: chrono::DateTime<Utc>| prost_types::Timestamp { seconds: t.timestamp(), nanos: 0 };
+        RedeemApproval {
+            approval_id: a.approval_id.to_string(),
+            redeem_id: a.redeem_id.to_string(),
+            reward_name: a.reward_name.clone(),
+            channel: a.channel.clone(),
+            user_name: a.user_name.clone(),
+            user_input: a.user_input.clone(),
+            status: a.status.to_string(),
+            requested_at: Some(ts(a.requested_at)),
+            decided_at: a.decided_at.map(ts),
+            decided_by: a.decided_by.clone().unwrap_or_default(),
+        }
+    }
+
     fn redeem_to_proto(rd: &maowbot_common::models::redeem::Redeem) -> common::Redeem {
         let mut metadata = HashMap::new();
         metadata.insert("dynamic_pricing".to_string(), rd.dynamic_pricing.to_string());
         metadata.insert("active_offline".to_string(), rd.active_offline.to_string());
         metadata.insert("is_managed".to_string(), rd.is_managed.to_string());
         metadata.insert("is_input_required".to_string(), rd.is_input_required.to_string());
+        metadata.insert("requires_approval".to_string(), rd.requires_approval.to_string());
         
         if let Some(plugin_name) = &rd.plugin_name {
             metadata.insert("plugin_name".to_string(), plugin_name.clone());
@@ -106,6 +160,10 @@ impl RedeemServiceImpl {
             .and_then(|s| s.parse::<bool>().ok())
             .unwrap_or(false);
             
+        let requires_approval = proto.metadata.get("requires_approval")
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(false);
+            
         let active_credential_id = proto.metadata.get("active_credential_id")
             .and_then(|id| Uuid::parse_str(id).ok());
             
@@ -130,6 +188,7 @@ impl RedeemServiceImpl {
             active_credential_id,
             is_input_required,
             redeem_prompt_text,
+            requires_approval,
         })
     }
 }
@@ -137,24 +196,47 @@ impl RedeemServiceImpl {
 #[tonic::async_trait]
 impl RedeemService for RedeemServiceImpl {
     async fn list_redeems(&self, request: Request<ListRedeemsRequest>) -> Result<Response<ListRedeemsResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Listing redeems - platform: {:?}, active_only: {}, dynamic_only: {}", 
                req.platform, req.active_only, req.dynamic_only);
+
+        let page = Page::from_request(req.page.as_ref())?;
+        let mask = ReadMask::parse(req.read_mask.as_ref(), &["redeem", "stats", "sync_status", "linked_triggers"])?;
+        let order_by = sort_field(&req.order_by, &["name", "cost", "created_at"])?;
+        let name_prefix = req.name_prefix.to_lowercase();
         
         // Get redeems for platform
         let redeems = if req.platform.is_empty() {
             // If no platform specified, we'd need to list all - for now return empty
             vec![]
         } else {
-            self.redeem_repo.list_redeems(&req.platform).await
+            redeem_repo.list_redeems(&req.platform).await
                 .map_err(|e| Status::internal(format!("Failed to list redeems: {}", e)))?
         };
         
         // Filter by active_only and dynamic_only if requested
-        let filtered_redeems: Vec<_> = redeems.into_iter()
+        let mut filtered_redeems: Vec<_> = redeems.into_iter()
             .filter(|rd| !req.active_only || rd.is_active)
             .filter(|rd| !req.dynamic_only || rd.dynamic_pricing)
+            .filter(|rd| name_prefix.is_empty() || rd.reward_name.to_lowercase().starts_with(&name_prefix))
             .collect();
+
+        // Name breaks ties so pages stay stable
+        filtered_redeems.sort_by(|a, b| {
+            let ordering = match order_by {
+                "cost" => a.cost.cmp(&b.cost),
+                "created_at" => a.created_at.cmp(&b.created_at),
+                _ => std::cmp::Ordering::Equal,
+            };
+            ordering
+                .then_with(|| a.reward_name.to_lowercase().cmp(&b.reward_name.to_lowercase()))
+                .then_with(|| a.redeem_id.cmp(&b.redeem_id))
+        });
+        if req.descending {
+            filtered_redeems.reverse();
+        }
+        let (filtered_redeems, page) = page.slice(filtered_redeems);
         
         // Convert to proto format
         let mut redeem_infos = Vec::new();
@@ -183,27 +265,27 @@ impl RedeemService for RedeemServiceImpl {
             let linked_triggers = vec![];
             
             redeem_infos.push(RedeemInfo {
-                redeem: Some(Self::redeem_to_proto(&rd)),
-                stats: Some(stats),
-                sync_status: Some(sync_status),
-                linked_triggers,
+                redeem: mask.includes("redeem").then(|| Self::redeem_to_proto(&rd)),
+                stats: mask.includes("stats").then_some(stats),
+                sync_status: mask.includes("sync_status").then_some(sync_status),
+                linked_triggers: if mask.includes("linked_triggers") { linked_triggers } else { vec![] },
             });
         }
         
-        // TODO: Implement proper pagination
         Ok(Response::new(ListRedeemsResponse {
             redeems: redeem_infos,
-            page: None,
+            page: Some(page),
         }))
     }
     async fn get_redeem(&self, request: Request<GetRedeemRequest>) -> Result<Response<GetRedeemResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         let redeem_id = Uuid::parse_str(&req.redeem_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
         
         debug!("Getting redeem: {}", redeem_id);
         
-        let rd = self.redeem_repo.get_redeem_by_id(redeem_id).await
+        let rd = redeem_repo.get_redeem_by_id(redeem_id).await
             .map_err(|e| Status::internal(format!("Failed to get redeem: {}", e)))?;
         
         let rd = match rd {
@@ -253,7 +335,7 @@ impl RedeemService for RedeemServiceImpl {
         let stats = RedeemStatistics {
             total_redemptions: recent_usage.len() as i64,
             unique_users: 0, // TODO: Calculate unique users
-            last_redeemed: recent_usage.first().and_then(|u| u.redeemed_at.clone()),
+            last_redeemed: recent_usage.first().and_then(|u| u.redeemed_at),
             average_time_between_redemptions: 0.0,
             total_points_spent: rd.cost as i64 * recent_usage.len() as i64,
         };
@@ -281,6 +363,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn create_redeem(&self, request: Request<CreateRedeemRequest>) -> Result<Response<CreateRedeemResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         let proto_rd = req.redeem.ok_or_else(|| Status::invalid_argument("Redeem is required"))?;
         
@@ -298,7 +381,7 @@ impl RedeemService for RedeemServiceImpl {
         }
         
         // Check if redeem already exists
-        let existing = self.redeem_repo.get_redeem_by_reward_id(&proto_rd.platform, &proto_rd.reward_id).await
+        let existing = redeem_repo.get_redeem_by_reward_id(&proto_rd.platform, &proto_rd.reward_id).await
             .map_err(|e| Status::internal(format!("Failed to check existing redeem: {}", e)))?;
         
         if existing.is_some() {
@@ -312,11 +395,11 @@ impl RedeemService for RedeemServiceImpl {
         rd.updated_at = Utc::now();
         
         // Create the redeem
-        self.redeem_repo.create_redeem(&rd).await
+        redeem_repo.create_redeem(&rd).await
             .map_err(|e| Status::internal(format!("Failed to create redeem: {}", e)))?;
         
         // TODO: Sync to platform if requested
-        let synced = req.sync_to_platform && false; // Not implemented yet
+        let synced = false; // Not implemented yet, whatever sync_to_platform says
         
         Ok(Response::new(CreateRedeemResponse {
             redeem: Some(Self::redeem_to_proto(&rd)),
@@ -324,6 +407,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn update_redeem(&self, request: Request<UpdateRedeemRequest>) -> Result<Response<UpdateRedeemResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         let redeem_id = Uuid::parse_str(&req.redeem_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
@@ -331,7 +415,7 @@ impl RedeemService for RedeemServiceImpl {
         info!("Updating redeem: {}", redeem_id);
         
         // Get existing redeem
-        let existing = self.redeem_repo.get_redeem_by_id(redeem_id).await
+        let existing = redeem_repo.get_redeem_by_id(redeem_id).await
             .map_err(|e| Status::internal(format!("Failed to get redeem: {}", e)))?;
         
         let mut existing = match existing {
@@ -360,6 +444,9 @@ impl RedeemService for RedeemServiceImpl {
                     "prompt_text" => existing.redeem_prompt_text = proto_rd.metadata.get("prompt_text")
                         .filter(|s| !s.is_empty())
                         .cloned(),
+                    "requires_approval" => existing.requires_approval = proto_rd.metadata.get("requires_approval")
+                        .and_then(|s| s.parse::<bool>().ok())
+                        .unwrap_or(existing.requires_approval),
                     "active_credential_id" => {
                         existing.active_credential_id = if let Some(id) = proto_rd.metadata.get("active_credential_id") {
                             Some(Uuid::parse_str(id)
@@ -388,6 +475,9 @@ impl RedeemService for RedeemServiceImpl {
             existing.redeem_prompt_text = proto_rd.metadata.get("prompt_text")
                 .filter(|s| !s.is_empty())
                 .cloned();
+            existing.requires_approval = proto_rd.metadata.get("requires_approval")
+                .and_then(|s| s.parse::<bool>().ok())
+                .unwrap_or(existing.requires_approval);
             existing.active_credential_id = proto_rd.metadata.get("active_credential_id")
                 .and_then(|id| Uuid::parse_str(id).ok());
         }
@@ -395,11 +485,11 @@ impl RedeemService for RedeemServiceImpl {
         existing.updated_at = Utc::now();
         
         // Update the redeem
-        self.redeem_repo.update_redeem(&existing).await
+        redeem_repo.update_redeem(&existing).await
             .map_err(|e| Status::internal(format!("Failed to update redeem: {}", e)))?;
         
         // TODO: Sync to platform if requested
-        let synced = req.sync_to_platform && false; // Not implemented yet
+        let synced = false; // Not implemented yet, whatever sync_to_platform says
         
         Ok(Response::new(UpdateRedeemResponse {
             redeem: Some(Self::redeem_to_proto(&existing)),
@@ -407,6 +497,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn delete_redeem(&self, request: Request<DeleteRedeemRequest>) -> Result<Response<()>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         let redeem_id = Uuid::parse_str(&req.redeem_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
@@ -419,12 +510,13 @@ impl RedeemService for RedeemServiceImpl {
         }
         
         // Delete the redeem
-        self.redeem_repo.delete_redeem(redeem_id).await
+        redeem_repo.delete_redeem(redeem_id).await
             .map_err(|e| Status::internal(format!("Failed to delete redeem: {}", e)))?;
         
         Ok(Response::new(()))
     }
     async fn batch_list_redeems(&self, request: Request<BatchListRedeemsRequest>) -> Result<Response<BatchListRedeemsResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Batch listing redeems for {} platforms", req.platforms.len());
         
@@ -433,7 +525,7 @@ impl RedeemService for RedeemServiceImpl {
         
         // Get redeems for each platform
         for platform in &req.platforms {
-            let redeems = self.redeem_repo.list_redeems(platform).await
+            let redeems = redeem_repo.list_redeems(platform).await
                 .map_err(|e| Status::internal(format!("Failed to list redeems for {}: {}", platform, e)))?;
             
             let active_count = redeems.iter().filter(|r| r.is_active).count() as i32;
@@ -493,6 +585,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn batch_update_redeems(&self, request: Request<BatchUpdateRedeemsRequest>) -> Result<Response<BatchUpdateRedeemsResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         info!("Batch updating {} redeems", req.updates.len());
         
@@ -520,7 +613,7 @@ impl RedeemService for RedeemServiceImpl {
             };
             
             // Get existing redeem
-            let existing = match self.redeem_repo.get_redeem_by_id(redeem_id).await {
+            let existing = match redeem_repo.get_redeem_by_id(redeem_id).await {
                 Ok(Some(r)) => r,
                 Ok(None) => {
                     failure_count += 1;
@@ -577,10 +670,10 @@ impl RedeemService for RedeemServiceImpl {
             updated.updated_at = Utc::now();
             
             // Save the update
-            match self.redeem_repo.update_redeem(&updated).await {
+            match redeem_repo.update_redeem(&updated).await {
                 Ok(_) => {
                     success_count += 1;
-                    let synced = req.sync_all && false; // TODO: Implement sync
+                    let synced = false; // TODO: Implement sync for sync_all
                     results.push(UpdateRedeemResult {
                         redeem_id: update.redeem_id,
                         success: true,
@@ -612,48 +705,72 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn sync_redeems(&self, request: Request<SyncRedeemsRequest>) -> Result<Response<SyncRedeemsResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
-        info!("Syncing redeems - platforms: {:?}, direction: {:?}, dry_run: {}", 
+        info!("Syncing redeems - platforms: {:?}, direction: {:?}, dry_run: {}",
               req.platforms, req.direction, req.dry_run);
-        
-        // TODO: Implement actual sync logic with platforms
-        // For now, return a mock response
-        
-        let mut results = Vec::new();
-        let created_count = 0;
-        let updated_count = 0;
-        let deleted_count = 0;
-        let error_count = 0;
-        
-        if req.dry_run {
-            debug!("Dry run mode - no actual changes will be made");
+
+        if req.platforms.iter().any(|p| p != "twitch-eventsub" && p != "twitch") {
+            return Err(Status::invalid_argument("Only Twitch channel point redeems can be synced"));
         }
-        
-        // Mock some sync results
-        for platform in &req.platforms {
-            let redeems = self.redeem_repo.list_redeems(platform).await
-                .map_err(|e| Status::internal(format!("Failed to list redeems: {}", e)))?;
-            
-            for rd in redeems.iter().take(3) { // Just mock first 3
-                results.push(SyncResult {
-                    redeem_id: rd.redeem_id.to_string(),
-                    platform: platform.clone(),
-                    action: SyncAction::Skipped as i32,
-                    success: true,
-                    error_message: String::new(),
-                });
+        let direction = match SyncDirection::try_from(req.direction).unwrap_or(SyncDirection::Unknown) {
+            SyncDirection::ToPlatform => redeem_sync::SyncDirection::ToTwitch,
+            SyncDirection::FromPlatform => redeem_sync::SyncDirection::FromTwitch,
+            SyncDirection::Bidirectional | SyncDirection::Unknown => redeem_sync::SyncDirection::Both,
+        };
+
+        let synced = redeem_sync::sync_redeems_with_twitch(
+            redeem_repo.as_ref(),
+            self.redeem_service.credentials_repo.as_ref(),
+            direction,
+            req.dry_run,
+        )
+        .await
+        .map_err(|e| match e {
+            maowbot_core::Error::Platform(msg) => Status::failed_precondition(msg),
+            other => Status::internal(format!("Redeem sync failed: {}", other)),
+        })?;
+
+        let (mut created_count, mut updated_count, mut error_count) = (0, 0, 0);
+        let results = synced.into_iter().map(|r| {
+            let action = match r.change.kind {
+                RedeemChangeKind::Import | RedeemChangeKind::CreateOnTwitch => SyncAction::Created,
+                RedeemChangeKind::Link | RedeemChangeKind::PushToTwitch | RedeemChangeKind::PullFromTwitch => SyncAction::Updated,
+                RedeemChangeKind::MissingOnTwitch => SyncAction::Skipped,
+            };
+            if r.error.is_some() {
+                error_count += 1;
+            } else if r.applied && action == SyncAction::Created {
+                created_count += 1;
+            } else if r.applied && action == SyncAction::Updated {
+                updated_count += 1;
             }
-        }
-        
+            SyncResult {
+                redeem_id: r.change.redeem.as_ref().map(|rd| rd.redeem_id.to_string()).unwrap_or_default(),
+                platform: "twitch-eventsub".to_string(),
+                action: action as i32,
+                success: r.error.is_none(),
+                error_message: r.error.unwrap_or_default(),
+                reward_name: r.change.reward_name,
+                reward_id: r.change.reward.as_ref().map(|rw| rw.id.clone())
+                    .or_else(|| r.change.redeem.as_ref().map(|rd| rd.reward_id.clone()))
+                    .unwrap_or_default(),
+                change: r.change.kind.to_string(),
+                differences: r.change.differences,
+                applied: r.applied,
+            }
+        }).collect();
+
         Ok(Response::new(SyncRedeemsResponse {
             results,
             created_count,
             updated_count,
-            deleted_count,
+            deleted_count: 0,
             error_count,
         }))
     }
     async fn get_sync_status(&self, request: Request<GetSyncStatusRequest>) -> Result<Response<GetSyncStatusResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Getting sync status for platforms: {:?}", req.platforms);
         
@@ -667,7 +784,7 @@ impl RedeemService for RedeemServiceImpl {
         };
         
         for platform in platforms {
-            let redeems = self.redeem_repo.list_redeems(&platform).await
+            let redeems = redeem_repo.list_redeems(&platform).await
                 .map_err(|e| Status::internal(format!("Failed to list redeems: {}", e)))?;
             
             let local_count = redeems.len() as i32;
@@ -698,6 +815,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn execute_redeem(&self, request: Request<ExecuteRedeemRequest>) -> Result<Response<ExecuteRedeemResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         let redeem_id = Uuid::parse_str(&req.redeem_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
@@ -707,7 +825,7 @@ impl RedeemService for RedeemServiceImpl {
         debug!("Executing redeem: {} for user: {}", redeem_id, user_id);
         
         // Get the redeem
-        let rd = self.redeem_repo.get_redeem_by_id(redeem_id).await
+        let rd = redeem_repo.get_redeem_by_id(redeem_id).await
             .map_err(|e| Status::internal(format!("Failed to get redeem: {}", e)))?;
         
         let rd = match rd {
@@ -760,6 +878,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn test_redeem(&self, request: Request<TestRedeemRequest>) -> Result<Response<TestRedeemResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         let redeem_id = Uuid::parse_str(&req.redeem_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
@@ -767,7 +886,7 @@ impl RedeemService for RedeemServiceImpl {
         debug!("Testing redeem: {}", redeem_id);
         
         // Get the redeem
-        let rd = self.redeem_repo.get_redeem_by_id(redeem_id).await
+        let rd = redeem_repo.get_redeem_by_id(redeem_id).await
             .map_err(|e| Status::internal(format!("Failed to get redeem: {}", e)))?;
         
         let rd = match rd {
@@ -788,10 +907,8 @@ impl RedeemService for RedeemServiceImpl {
         
         if would_execute {
             // List what would be triggered
-            if rd.plugin_name.is_some() && rd.command_name.is_some() {
-                triggered_actions.push(format!("Plugin: {}, Command: {}", 
-                    rd.plugin_name.as_ref().unwrap(), 
-                    rd.command_name.as_ref().unwrap()));
+            if let (Some(plugin_name), Some(command_name)) = (&rd.plugin_name, &rd.command_name) {
+                triggered_actions.push(format!("Plugin: {}, Command: {}", plugin_name, command_name));
             }
             
             // Check for OSC triggers
@@ -815,6 +932,7 @@ impl RedeemService for RedeemServiceImpl {
         }))
     }
     async fn get_redeem_usage(&self, request: Request<GetRedeemUsageRequest>) -> Result<Response<GetRedeemUsageResponse>, Status> {
+        let redeem_repo = self.redeem_repo_for(&request).await?;
         let req = request.into_inner();
         debug!("Getting redeem usage");
         
@@ -826,7 +944,7 @@ impl RedeemService for RedeemServiceImpl {
                 .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
             
             // Get the redeem first
-            let rd = self.redeem_repo.get_redeem_by_id(redeem_id).await
+            let rd = redeem_repo.get_redeem_by_id(redeem_id).await
                 .map_err(|e| Status::internal(format!("Failed to get redeem: {}", e)))?;
             
             if let Some(rd) = rd {
@@ -864,7 +982,7 @@ impl RedeemService for RedeemServiceImpl {
             }
         } else if !req.platform.is_empty() {
             // Get all redeems for platform and their usage
-            let redeems = self.redeem_repo.list_redeems(&req.platform).await
+            let redeems = redeem_repo.list_redeems(&req.platform).await
                 .map_err(|e| Status::internal(format!("Failed to list redeems: {}", e)))?;
             
             for rd in redeems {
@@ -936,4 +1054,102 @@ impl RedeemService for RedeemServiceImpl {
         // TODO: Implement streaming of redeem events
         Err(Status::unimplemented("Redeem event streaming not yet implemented"))
     }
-}
\ No newline at end of file
+
+    async fn list_redeem_schedules(&self, _: Request<ListRedeemSchedulesRequest>) -> Result<Response<ListRedeemSchedulesResponse>, Status> {
+        let scheduled = self.schedule_service.status().await.map_err(schedule_status)?;
+        Ok(Response::new(ListRedeemSchedulesResponse {
+            redeems: scheduled.iter().map(|s| ScheduledRedeemInfo {
+                redeem_id: s.redeem.redeem_id.to_string(),
+                reward_name: s.redeem.reward_name.clone(),
+                base_cost: s.redeem.cost,
+                base_enabled: s.redeem.is_active,
+                current_cost: s.state.cost as i32,
+                current_enabled: s.state.is_enabled,
+                schedules: s.schedules.iter()
+                    .map(|sch| Self::schedule_to_proto(sch, s.applying.contains(&sch.name)))
+                    .collect(),
+            }).collect(),
+        }))
+    }
+
+    async fn add_redeem_schedule(&self, request: Request<AddRedeemScheduleRequest>) -> Result<Response<RedeemScheduleInfo>, Status> {
+        let req = request.into_inner();
+        let redeem_id = Uuid::parse_str(&req.redeem_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid redeem ID: {}", e)))?;
+        let condition = req.condition.parse().map_err(|e: maowbot_core::Error| Status::invalid_argument(e.to_string()))?;
+        let action = req.action.parse().map_err(|e: maowbot_core::Error| Status::invalid_argument(e.to_string()))?;
+        let schedule = self.schedule_service
+            .add_schedule(
+                redeem_id,
+                &req.name,
+                condition,
+                &req.condition_value,
+                action,
+                Some(req.amount).filter(|a| *a != 0.0),
+                req.priority,
+            )
+            .await
+            .map_err(schedule_status)?;
+        Ok(Response::new(Self::schedule_to_proto(&schedule, false)))
+    }
+
+    async fn remove_redeem_schedule(&self, request: Request<RemoveRedeemScheduleRequest>) -> Result<Response<()>, Status> {
+        let schedule_id = Uuid::parse_str(&request.into_inner().schedule_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid schedule ID: {}", e)))?;
+        self.schedule_service.remove_schedule(schedule_id).await.map_err(schedule_status)?;
+        Ok(Response::new(()))
+    }
+
+    async fn set_redeem_schedule_enabled(&self, request: Request<SetRedeemScheduleEnabledRequest>) -> Result<Response<RedeemScheduleInfo>, Status> {
+        let req = request.into_inner();
+        let schedule_id = Uuid::parse_str(&req.schedule_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid schedule ID: {}", e)))?;
+        let schedule = self.schedule_service.set_schedule_enabled(schedule_id, req.enabled).await.map_err(schedule_status)?;
+        Ok(Response::new(Self::schedule_to_proto(&schedule, false)))
+    }
+
+    async fn list_redeem_approvals(&self, _: Request<ListRedeemApprovalsRequest>) -> Result<Response<ListRedeemApprovalsResponse>, Status> {
+        let pending = self.approval_service.list_pending().await.map_err(approval_status)?;
+        Ok(Response::new(ListRedeemApprovalsResponse {
+            approvals: pending.iter().map(Self::approval_to_proto).collect(),
+        }))
+    }
+
+    async fn approve_redeem(&self, request: Request<DecideRedeemRequest>) -> Result<Response<RedeemApproval>, Status> {
+        let caller = caller_name(&request);
+        let approval_id = Uuid::parse_str(&request.into_inner().approval_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid approval ID: {}", e)))?;
+        let approval = self.approval_service.approve(approval_id, &caller).await.map_err(approval_status)?;
+        Ok(Response::new(Self::approval_to_proto(&approval)))
+    }
+
+    async fn deny_redeem(&self, request: Request<DecideRedeemRequest>) -> Result<Response<RedeemApproval>, Status> {
+        let caller = caller_name(&request);
+        let approval_id = Uuid::parse_str(&request.into_inner().approval_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid approval ID: {}", e)))?;
+        let approval = self.approval_service.deny(approval_id, &caller).await.map_err(approval_status)?;
+        Ok(Response::new(Self::approval_to_proto(&approval)))
+    }
+}
+
+fn caller_name<T>(request: &Request<T>) -> String {
+    request.extensions().get::<Caller>()
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "console".to_string())
+}
+
+fn approval_status(e: maowbot_core::Error) -> Status {
+    match e {
+        maowbot_core::Error::NotFound(msg) => Status::not_found(msg),
+        maowbot_core::Error::ValidationError(msg) => Status::failed_precondition(msg),
+        other => Status::internal(other.to_string()),
+    }
+}
+
+fn schedule_status(e: maowbot_core::Error) -> Status {
+    match e {
+        maowbot_core::Error::NotFound(msg) => Status::not_found(msg),
+        maowbot_core::Error::Parse(msg) => Status::invalid_argument(msg),
+        other => Status::internal(other.to_string()),
+    }
+}
@@ -0,0 +1,132 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    responder_service_server::ResponderService,
+    ChatResponder, ResponderResponse, ResponderHit,
+    ListRespondersRequest, ListRespondersResponse,
+    AddResponderRequest, UpdateResponderRequest,
+    DeleteResponderRequest, DeleteResponderResponse,
+    TestRespondersRequest, TestRespondersResponse,
+};
+use maowbot_common::models::responder::{Responder, ResponderMatch};
+use maowbot_core::services::responders::{NewResponder, ResponderEdit, ResponderService as Responders};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::authz::{audit_value, AuditNote, Caller};
+use super::convert::{to_timestamp, to_status};
+
+pub struct ResponderServiceImpl {
+    responders: Arc<Responders>,
+}
+
+impl ResponderServiceImpl {
+    pub fn new(responders: Arc<Responders>) -> Self {
+        Self { responders }
+    }
+}
+
+fn caller_name<T>(request: &Request<T>) -> String {
+    request.extensions().get::<Caller>()
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "console".to_string())
+}
+
+fn responder_to_proto(r: Responder) -> ChatResponder {
+    ChatResponder {
+        responder_id: r.responder_id.to_string(),
+        name: r.name,
+        match_kind: r.match_kind.to_string(),
+        pattern: r.pattern,
+        responses: r.responses,
+        platform: r.platform.unwrap_or_default(),
+        channel: r.channel.unwrap_or_default(),
+        chance_percent: r.chance_percent,
+        cooldown_secs: r.cooldown_secs,
+        user_cooldown_secs: r.user_cooldown_secs,
+        enabled: r.enabled,
+        hit_count: r.hit_count,
+        last_hit_at: r.last_hit_at.map(to_timestamp),
+    }
+}
+
+fn responder_response(responder: Responder) -> Response<ResponderResponse> {
+    Response::new(ResponderResponse { responder: Some(responder_to_proto(responder)) })
+}
+
+#[tonic::async_trait]
+impl ResponderService for ResponderServiceImpl {
+    async fn list_responders(&self, _request: Request<ListRespondersRequest>) -> Result<Response<ListRespondersResponse>, Status> {
+        let responders = self.responders.list_responders().await.map_err(to_status)?;
+        Ok(Response::new(ListRespondersResponse {
+            responders: responders.into_iter().map(responder_to_proto).collect(),
+        }))
+    }
+
+    async fn add_responder(&self, request: Request<AddResponderRequest>) -> Result<Response<ResponderResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let match_kind: ResponderMatch = req.match_kind.parse().map_err(to_status)?;
+        let responder = self.responders.add_responder(NewResponder {
+            name: req.name,
+            match_kind,
+            pattern: req.pattern,
+            responses: req.responses,
+            platform: Some(req.platform),
+            channel: Some(req.channel),
+            chance_percent: if req.chance_percent > 0 { req.chance_percent } else { 100 },
+            cooldown_secs: req.cooldown_secs,
+            user_cooldown_secs: req.user_cooldown_secs,
+        }).await.map_err(to_status)?;
+        info!("Responder '{}' added by '{}'", responder.name, caller);
+        audit.change(format!("responder:{}", responder.name), None, audit_value(&responder));
+        Ok(responder_response(responder))
+    }
+
+    async fn update_responder(&self, request: Request<UpdateResponderRequest>) -> Result<Response<ResponderResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let req = request.into_inner();
+        let before = self.responders.get_responder(&req.name).await.ok();
+        let match_kind = req.match_kind.map(|k| k.parse::<ResponderMatch>()).transpose().map_err(to_status)?;
+        let responder = self.responders.edit_responder(&req.name, ResponderEdit {
+            match_kind,
+            pattern: req.pattern,
+            responses: req.responses.map(|r| r.lines),
+            platform: req.platform,
+            channel: req.channel,
+            chance_percent: req.chance_percent,
+            cooldown_secs: req.cooldown_secs,
+            user_cooldown_secs: req.user_cooldown_secs,
+            enabled: req.enabled,
+        }).await.map_err(to_status)?;
+        info!("Responder '{}' updated by '{}'", responder.name, caller);
+        audit.change(format!("responder:{}", responder.name), before.as_ref().and_then(audit_value), audit_value(&responder));
+        Ok(responder_response(responder))
+    }
+
+    async fn delete_responder(&self, request: Request<DeleteResponderRequest>) -> Result<Response<DeleteResponderResponse>, Status> {
+        let caller = caller_name(&request);
+        let audit = AuditNote::of(&request);
+        let name = request.into_inner().name;
+        let before = self.responders.get_responder(&name).await.ok();
+        self.responders.delete_responder(&name).await.map_err(to_status)?;
+        info!("Responder '{}' deleted by '{}'", name, caller);
+        audit.change(format!("responder:{}", name), before.as_ref().and_then(audit_value), None);
+        Ok(Response::new(DeleteResponderResponse {}))
+    }
+
+    async fn test_responders(&self, request: Request<TestRespondersRequest>) -> Result<Response<TestRespondersResponse>, Status> {
+        let req = request.into_inner();
+        let platform = if req.platform.is_empty() { "twitch-irc" } else { req.platform.as_str() };
+        let user = if req.user.is_empty() { "tester" } else { req.user.as_str() };
+        let hits = self.responders.test(platform, &req.channel, &req.text, user);
+        Ok(Response::new(TestRespondersResponse {
+            hits: hits.into_iter().map(|h| ResponderHit {
+                name: h.name,
+                matched: h.matched,
+                response: h.response.unwrap_or_default(),
+            }).collect(),
+        }))
+    }
+}
@@ -0,0 +1,96 @@
+use tonic::{Request, Response, Status};
+use maowbot_proto::maowbot::services::{
+    stream_session_service_server::StreamSessionService,
+    StreamSession as ProtoStreamSession, SessionStats as ProtoSessionStats,
+    SessionChatter as ProtoSessionChatter, StreamMarker as ProtoStreamMarker,
+    ListSessionsRequest, ListSessionsResponse, GetSessionRequest, GetSessionResponse,
+};
+use maowbot_common::models::stream_session::{SessionScope, StreamSession};
+use maowbot_core::services::twitch::stream_session_service::StreamSessionService as Sessions;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use super::convert::to_status;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 200;
+const DEFAULT_TOP_CHATTERS: i64 = 10;
+
+pub struct StreamSessionServiceImpl {
+    sessions: Arc<Sessions>,
+}
+
+impl StreamSessionServiceImpl {
+    pub fn new(sessions: Arc<Sessions>) -> Self {
+        Self { sessions }
+    }
+}
+
+fn timestamp(at: DateTime<Utc>) -> Option<prost_types::Timestamp> {
+    Some(prost_types::Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    })
+}
+
+fn session_to_proto(s: StreamSession) -> ProtoStreamSession {
+    ProtoStreamSession {
+        live: s.is_live(),
+        duration_seconds: s.duration_seconds(),
+        session_id: s.session_id.to_string(),
+        platform: s.platform,
+        channel: s.channel,
+        stream_id: s.stream_id,
+        title: s.title,
+        category: s.category,
+        started_at: timestamp(s.started_at),
+        ended_at: s.ended_at.and_then(timestamp),
+        stats: Some(ProtoSessionStats {
+            chat_messages: s.stats.chat_messages,
+            unique_chatters: s.stats.unique_chatters,
+            follows: s.stats.follows,
+            subs: s.stats.subs,
+            gifted_subs: s.stats.gifted_subs,
+            bits: s.stats.bits,
+            raids: s.stats.raids,
+            redemptions: s.stats.redemptions,
+            hype_moments: s.stats.hype_moments,
+            peak_viewers: s.stats.peak_viewers,
+        }),
+    }
+}
+
+#[tonic::async_trait]
+impl StreamSessionService for StreamSessionServiceImpl {
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status> {
+        let req = request.into_inner();
+        let limit = if req.limit > 0 { (req.limit as i64).min(MAX_LIMIT) } else { DEFAULT_LIMIT };
+        let sessions = self.sessions.list(limit).await.map_err(to_status)?;
+        Ok(Response::new(ListSessionsResponse {
+            sessions: sessions.into_iter().map(session_to_proto).collect(),
+        }))
+    }
+
+    async fn get_session(&self, request: Request<GetSessionRequest>) -> Result<Response<GetSessionResponse>, Status> {
+        let req = request.into_inner();
+        let scope = req.session.parse::<SessionScope>().map_err(to_status)?;
+        let top = if req.top_chatters > 0 { (req.top_chatters as i64).min(MAX_LIMIT) } else { DEFAULT_TOP_CHATTERS };
+        let (session, markers, chatters) = self.sessions.details(scope, top).await.map_err(to_status)?;
+        Ok(Response::new(GetSessionResponse {
+            session: Some(session_to_proto(session)),
+            markers: markers.into_iter().map(|m| ProtoStreamMarker {
+                marker_id: m.marker_id.to_string(),
+                stream_id: m.stream_id,
+                twitch_marker_id: m.twitch_marker_id.unwrap_or_default(),
+                position_seconds: m.position_seconds,
+                description: m.description,
+                source: m.source.to_string(),
+                created_at: timestamp(m.created_at),
+            }).collect(),
+            top_chatters: chatters.into_iter().map(|c| ProtoSessionChatter {
+                twitch_user_id: c.twitch_user_id,
+                display_name: c.display_name,
+                messages: c.messages,
+            }).collect(),
+        }))
+    }
+}
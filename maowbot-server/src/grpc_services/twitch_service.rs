@@ -1,25 +1,111 @@
 use tonic::{Request, Response, Status};
 use maowbot_proto::maowbot::services::{twitch_service_server::TwitchService, *};
 use maowbot_core::platforms::manager::PlatformManager;
-use maowbot_common::traits::api::TwitchApi;
+use maowbot_core::services::twitch::stream_marker_service::StreamMarkerService;
+use maowbot_core::services::twitch::schedule_service::{ScheduleEdit, ScheduleService};
+use maowbot_core::platforms::twitch::requests::schedule::ScheduleSegment as CoreScheduleSegment;
+use maowbot_core::platforms::twitch::requests::eventsub::EventSubSubscription as CoreEventSubSubscription;
+use maowbot_common::models::stream_marker::MarkerSource;
 use std::sync::Arc;
 use chrono::Utc;
-use tracing::{info, error, debug};
+use tracing::{info, debug};
 use prost_types;
+use crate::authz::AuditNote;
 use uuid::Uuid;
 
+/// Chapter lists returned by ListVodChapters when no limit is given.
+const DEFAULT_CHAPTERS_LIMIT: i64 = 20;
+
 pub struct TwitchServiceImpl {
     platform_manager: Arc<PlatformManager>,
+    stream_markers: Arc<StreamMarkerService>,
+    schedule: Arc<ScheduleService>,
 }
 
 impl TwitchServiceImpl {
-    pub fn new(platform_manager: Arc<PlatformManager>) -> Self {
+    pub fn new(
+        platform_manager: Arc<PlatformManager>,
+        stream_markers: Arc<StreamMarkerService>,
+        schedule: Arc<ScheduleService>,
+    ) -> Self {
         Self {
             platform_manager,
+            stream_markers,
+            schedule,
         }
     }
 }
 
+fn timestamp(at: chrono::DateTime<Utc>) -> Option<prost_types::Timestamp> {
+    Some(prost_types::Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    })
+}
+
+fn vod_chapters_to_proto(c: maowbot_common::models::stream_marker::VodChapters) -> VodChapters {
+    VodChapters {
+        stream_id: c.stream_id,
+        video_id: c.video_id.unwrap_or_default(),
+        video_url: c.video_url.unwrap_or_default(),
+        title: c.title,
+        chapters: c.chapters,
+        marker_count: c.marker_count,
+        exported_at: timestamp(c.exported_at),
+    }
+}
+
+fn schedule_segment_to_proto(s: CoreScheduleSegment) -> ScheduleSegment {
+    ScheduleSegment {
+        segment_id: s.id,
+        start_time: timestamp(s.start_time),
+        end_time: s.end_time.and_then(timestamp),
+        title: s.title,
+        category: s.category.map(|c| c.name).unwrap_or_default(),
+        is_recurring: s.is_recurring,
+        is_canceled: s.canceled_until.is_some(),
+        duration_minutes: s.end_time.map(|end| (end - s.start_time).num_minutes() as i32).unwrap_or_default(),
+    }
+}
+
+fn eventsub_subscription_to_proto(s: CoreEventSubSubscription, session_id: &str, stale_reason: Option<String>) -> EventSubSubscription {
+    EventSubSubscription {
+        current_session: s.transport.session_id.as_deref() == Some(session_id),
+        subscription_id: s.id,
+        r#type: s.sub_type,
+        version: s.version,
+        status: s.status,
+        cost: s.cost,
+        condition_json: s.condition.to_string(),
+        transport: s.transport.method,
+        created_at: s.created_at.and_then(timestamp),
+        stale_reason: stale_reason.unwrap_or_default(),
+    }
+}
+
+fn eventsub_status(e: maowbot_core::Error) -> Status {
+    match e {
+        maowbot_core::Error::NotFound(msg) => Status::failed_precondition(msg),
+        other => Status::internal(format!("EventSub request failed: {}", other)),
+    }
+}
+
+fn from_timestamp(ts: Option<prost_types::Timestamp>, field: &str) -> Result<chrono::DateTime<Utc>, Status> {
+    ts.and_then(|t| chrono::DateTime::from_timestamp(t.seconds, t.nanos.max(0) as u32))
+        .ok_or_else(|| Status::invalid_argument(format!("{} is required", field)))
+}
+
+fn schedule_status(e: maowbot_core::Error) -> Status {
+    match e {
+        maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+        other => Status::failed_precondition(format!("Schedule request failed: {}", other)),
+    }
+}
+
+fn duration_minutes(minutes: i32) -> Result<u32, Status> {
+    u32::try_from(minutes).map_err(|_| Status::invalid_argument("duration_minutes must be positive"))
+}
+
 #[tonic::async_trait]
 impl TwitchService for TwitchServiceImpl {
     async fn join_channel(&self, request: Request<JoinChannelRequest>) -> Result<Response<()>, Status> {
@@ -112,6 +198,7 @@ impl TwitchService for TwitchServiceImpl {
         Err(Status::unimplemented("Unban functionality not yet implemented"))
     }
     async fn timeout_user(&self, request: Request<TimeoutUserRequest>) -> Result<Response<()>, Status> {
+        let audit = AuditNote::of(&request);
         let req = request.into_inner();
         info!("Timing out user {} for {} seconds in channel {} - reason: {}", 
               req.user_id, req.duration_seconds, req.channel, req.reason);
@@ -129,6 +216,16 @@ impl TwitchService for TwitchServiceImpl {
         
         pm.timeout_twitch_user(&req.account_name, &channel, &req.user_id, req.duration_seconds as u32, reason).await
             .map_err(|e| Status::internal(format!("Failed to timeout user: {}", e)))?;
+        audit.change(
+            format!("twitch:{}/{}", channel, req.user_id),
+            None,
+            Some(serde_json::json!({
+                "action": "timeout",
+                "duration_seconds": req.duration_seconds,
+                "reason": req.reason,
+                "account": req.account_name,
+            })),
+        );
         
         Ok(Response::new(()))
     }
@@ -328,4 +425,155 @@ impl TwitchService for TwitchServiceImpl {
             failure_count,
         }))
     }
-}
\ No newline at end of file
+
+    async fn create_stream_marker(&self, request: Request<CreateStreamMarkerRequest>) -> Result<Response<CreateStreamMarkerResponse>, Status> {
+        let req = request.into_inner();
+        let description = if req.description.trim().is_empty() {
+            "Marker".to_string()
+        } else {
+            req.description.trim().to_string()
+        };
+
+        let marker = self.stream_markers.create_marker(MarkerSource::Manual, &description).await
+            .map_err(|e| Status::failed_precondition(format!("Failed to create marker: {}", e)))?;
+
+        Ok(Response::new(CreateStreamMarkerResponse {
+            marker: Some(StreamMarker {
+                marker_id: marker.marker_id.to_string(),
+                stream_id: marker.stream_id,
+                twitch_marker_id: marker.twitch_marker_id.unwrap_or_default(),
+                position_seconds: marker.position_seconds,
+                description: marker.description,
+                source: marker.source.to_string(),
+                created_at: timestamp(marker.created_at),
+            }),
+        }))
+    }
+
+    async fn list_vod_chapters(&self, request: Request<ListVodChaptersRequest>) -> Result<Response<ListVodChaptersResponse>, Status> {
+        let req = request.into_inner();
+        let limit = if req.limit > 0 { req.limit as i64 } else { DEFAULT_CHAPTERS_LIMIT };
+
+        let chapters = self.stream_markers.list_chapters(limit).await
+            .map_err(|e| Status::internal(format!("Failed to list VOD chapters: {}", e)))?;
+
+        Ok(Response::new(ListVodChaptersResponse {
+            chapters: chapters.into_iter().map(vod_chapters_to_proto).collect(),
+        }))
+    }
+
+    async fn get_vod_chapters(&self, request: Request<GetVodChaptersRequest>) -> Result<Response<GetVodChaptersResponse>, Status> {
+        let req = request.into_inner();
+        let chapters = self.stream_markers.get_chapters(req.id.trim()).await
+            .map_err(|e| Status::internal(format!("Failed to get VOD chapters: {}", e)))?
+            .ok_or_else(|| Status::not_found(format!("No chapters for '{}'", req.id)))?;
+
+        Ok(Response::new(GetVodChaptersResponse {
+            chapters: Some(vod_chapters_to_proto(chapters)),
+        }))
+    }
+
+    async fn list_schedule_segments(&self, request: Request<ListScheduleSegmentsRequest>) -> Result<Response<ListScheduleSegmentsResponse>, Status> {
+        let segments = if request.into_inner().refresh {
+            self.schedule.refresh().await
+        } else {
+            self.schedule.upcoming().await
+        }.map_err(schedule_status)?;
+
+        Ok(Response::new(ListScheduleSegmentsResponse {
+            segments: segments.into_iter().map(schedule_segment_to_proto).collect(),
+        }))
+    }
+
+    async fn create_schedule_segment(&self, request: Request<CreateScheduleSegmentRequest>) -> Result<Response<ScheduleSegmentResponse>, Status> {
+        let req = request.into_inner();
+        let start_time = from_timestamp(req.start_time, "start_time")?;
+        let timezone = Some(req.timezone.trim()).filter(|tz| !tz.is_empty());
+        let category = Some(req.category.trim()).filter(|c| !c.is_empty());
+
+        let segment = self.schedule
+            .create_segment(start_time, duration_minutes(req.duration_minutes)?, req.title.trim(), category, req.is_recurring, timezone)
+            .await
+            .map_err(schedule_status)?;
+
+        Ok(Response::new(ScheduleSegmentResponse {
+            segment: Some(schedule_segment_to_proto(segment)),
+        }))
+    }
+
+    async fn update_schedule_segment(&self, request: Request<UpdateScheduleSegmentRequest>) -> Result<Response<ScheduleSegmentResponse>, Status> {
+        let req = request.into_inner();
+        let segment = req.segment.ok_or_else(|| Status::invalid_argument("segment is required"))?;
+        let mask = req.update_mask.ok_or_else(|| Status::invalid_argument("update_mask is required"))?;
+
+        let mut edit = ScheduleEdit::default();
+        for path in &mask.paths {
+            match path.as_str() {
+                "start_time" => edit.start_time = Some(from_timestamp(segment.start_time, "start_time")?),
+                "duration_minutes" => edit.duration_minutes = Some(duration_minutes(segment.duration_minutes)?),
+                "title" => edit.title = Some(segment.title.trim().to_string()),
+                "category" => edit.category = Some(segment.category.trim().to_string()),
+                "is_canceled" => edit.canceled = Some(segment.is_canceled),
+                other => return Err(Status::invalid_argument(format!("Unknown field '{}' in update_mask", other))),
+            }
+        }
+
+        let updated = self.schedule.update_segment(req.segment_id.trim(), edit).await
+            .map_err(schedule_status)?;
+
+        Ok(Response::new(ScheduleSegmentResponse {
+            segment: Some(schedule_segment_to_proto(updated)),
+        }))
+    }
+
+    async fn delete_schedule_segment(&self, request: Request<DeleteScheduleSegmentRequest>) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        self.schedule.delete_segment(req.segment_id.trim()).await
+            .map_err(schedule_status)?;
+        Ok(Response::new(()))
+    }
+
+    async fn list_event_sub_subscriptions(&self, _request: Request<ListEventSubSubscriptionsRequest>) -> Result<Response<ListEventSubSubscriptionsResponse>, Status> {
+        let overview = self.platform_manager.eventsub_subscriptions.overview().await
+            .map_err(eventsub_status)?;
+        let session_id = overview.session_id.unwrap_or_default();
+        let stale = overview.stale;
+        let subscriptions = overview.subscriptions.into_iter()
+            .map(|s| {
+                let reason = stale.iter().find(|(id, _)| *id == s.id).map(|(_, reason)| reason.clone());
+                eventsub_subscription_to_proto(s, &session_id, reason)
+            })
+            .collect();
+
+        Ok(Response::new(ListEventSubSubscriptionsResponse {
+            subscriptions,
+            total_cost: overview.total_cost,
+            max_total_cost: overview.max_total_cost,
+            session_id,
+            missing: overview.missing,
+        }))
+    }
+
+    async fn repair_event_sub_subscriptions(&self, request: Request<RepairEventSubSubscriptionsRequest>) -> Result<Response<RepairEventSubSubscriptionsResponse>, Status> {
+        let audit = AuditNote::of(&request);
+        let report = self.platform_manager.eventsub_subscriptions.repair().await
+            .map_err(eventsub_status)?;
+        let session_id = self.platform_manager.eventsub_subscriptions.session_id().unwrap_or_default();
+        audit.change("twitch:eventsub", None, Some(serde_json::json!({
+            "created": report.created,
+            "pruned": report.pruned.iter().map(|(s, reason)| format!("{} v{} ({})", s.sub_type, s.version, reason)).collect::<Vec<_>>(),
+        })));
+
+        Ok(Response::new(RepairEventSubSubscriptionsResponse {
+            created: report.created,
+            unchanged: report.unchanged as i32,
+            pruned: report.pruned.into_iter()
+                .map(|(s, reason)| eventsub_subscription_to_proto(s, &session_id, Some(reason)))
+                .collect(),
+            failed: report.failed.into_iter().map(|(what, e)| format!("{}: {}", what, e)).collect(),
+            over_budget: report.over_budget,
+            total_cost: report.total_cost,
+            max_total_cost: report.max_total_cost,
+        }))
+    }
+}
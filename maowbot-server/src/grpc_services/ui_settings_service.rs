@@ -0,0 +1,120 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tracing::{debug, info};
+use maowbot_proto::maowbot::services::{
+    ui_settings_service_server::UiSettingsService,
+    GetUiSettingsRequest, GetUiSettingsResponse, StreamUiSettingsRequest,
+    UiSettingsSection as ProtoUiSettingsSection, UpdateUiSettingsRequest,
+};
+use maowbot_core::services::ui_settings::{UiSettingsSection, UiSettingsStore};
+
+/// Changes queued per client before the stream applies backpressure.
+const CLIENT_BUFFER: usize = 16;
+
+pub struct UiSettingsServiceImpl {
+    store: Arc<UiSettingsStore>,
+}
+
+impl UiSettingsServiceImpl {
+    pub fn new(store: Arc<UiSettingsStore>) -> Self {
+        Self { store }
+    }
+}
+
+fn section_to_proto(section: &UiSettingsSection) -> ProtoUiSettingsSection {
+    ProtoUiSettingsSection {
+        section: section.section.clone(),
+        settings_json: section.settings.to_string(),
+        revision: section.revision,
+        updated_by: section.updated_by.clone(),
+        updated_at: Some(prost_types::Timestamp {
+            seconds: section.updated_at.timestamp(),
+            nanos: section.updated_at.timestamp_subsec_nanos() as i32,
+        }),
+    }
+}
+
+/// Whether `section` is one of `wanted`, or `wanted` is empty.
+fn is_wanted(wanted: &[String], section: &str) -> bool {
+    wanted.is_empty() || wanted.iter().any(|w| w == section)
+}
+
+#[tonic::async_trait]
+impl UiSettingsService for UiSettingsServiceImpl {
+    type StreamUiSettingsStream = Pin<Box<dyn Stream<Item = Result<ProtoUiSettingsSection, Status>> + Send>>;
+
+    async fn get_ui_settings(
+        &self,
+        request: Request<GetUiSettingsRequest>,
+    ) -> Result<Response<GetUiSettingsResponse>, Status> {
+        let wanted = request.into_inner().sections;
+        Ok(Response::new(GetUiSettingsResponse {
+            sections: self.store.get_all().iter()
+                .filter(|s| is_wanted(&wanted, &s.section))
+                .map(section_to_proto)
+                .collect(),
+        }))
+    }
+
+    async fn update_ui_settings(
+        &self,
+        request: Request<UpdateUiSettingsRequest>,
+    ) -> Result<Response<ProtoUiSettingsSection>, Status> {
+        let req = request.into_inner();
+        let settings = serde_json::from_str(&req.settings_json)
+            .map_err(|e| Status::invalid_argument(format!("settings_json is not valid JSON: {}", e)))?;
+        let saved = self.store.update(&req.section, settings, &req.client_id).await
+            .map_err(|e| match e {
+                maowbot_core::Error::ValidationError(msg) => Status::invalid_argument(msg),
+                other => Status::internal(other.to_string()),
+            })?;
+        info!("UI settings '{}' updated by '{}'", saved.section, saved.updated_by);
+        Ok(Response::new(section_to_proto(&saved)))
+    }
+
+    async fn stream_ui_settings(
+        &self,
+        request: Request<StreamUiSettingsRequest>,
+    ) -> Result<Response<Self::StreamUiSettingsStream>, Status> {
+        let wanted = request.into_inner().sections;
+        // Subscribe before reading so nothing saved in between is missed
+        let mut changes = self.store.subscribe();
+        let store = self.store.clone();
+
+        let (tx, rx) = mpsc::channel(CLIENT_BUFFER);
+        tokio::spawn(async move {
+            let mut last_revision = 0;
+            loop {
+                for section in store.get_all() {
+                    last_revision = last_revision.max(section.revision);
+                    if is_wanted(&wanted, &section.section) && tx.send(Ok(section_to_proto(&section))).await.is_err() {
+                        return;
+                    }
+                }
+                loop {
+                    match changes.recv().await {
+                        Ok(section) if section.revision <= last_revision => {}
+                        Ok(section) => {
+                            last_revision = section.revision;
+                            if is_wanted(&wanted, &section.section) && tx.send(Ok(section_to_proto(&section))).await.is_err() {
+                                return;
+                            }
+                        }
+                        // Settings are state, so a lagging client just gets all of them again
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            debug!("UI settings subscriber lagged by {} change(s); resending", n);
+                            break;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
@@ -1,48 +1,112 @@
 use tonic::{Request, Response, Status};
 use prost_types;
 use maowbot_proto::maowbot::{
-    common::{User, PlatformIdentity, UserAnalysis, Platform, PageRequest, PageResponse},
+    common::{User, PlatformIdentity, Platform},
     services::{
         user_service_server::UserService,
         MergeStrategy,
         *,
     },
 };
-use maowbot_core::repositories::postgres::{
-    user::UserRepository,
-    user_analysis::PostgresUserAnalysisRepository,
-    platform_identity::PlatformIdentityRepository,
-};
 use maowbot_common::{
     models::{
+        user::{UserListQuery, UserSort},
         user as user_models,
         platform::PlatformIdentity as PlatformIdentityModel,
         user_analysis::UserAnalysis as UserAnalysisModel,
+        user_notes::{ModerationAction as ModerationActionModel, ModerationActionType, UserNote as UserNoteModel},
     },
-    traits::repository_traits::{UserAnalysisRepository, UserRepo, PlatformIdentityRepo},
+    traits::repository_traits::{UserAnalysisRepository, UserNotesRepository, UserRepo, PlatformIdentityRepo},
 };
+use maowbot_core::services::viewer_card::ViewerCardService;
+use maowbot_core::tasks::analysis_recompute::{AnalysisRecompute, RecomputeRun};
+use crate::authz::Caller;
+use crate::grpc_services::paging::{sort_field, Page, ReadMask};
 use std::sync::Arc;
 use std::str::FromStr;
 use uuid::Uuid;
 use chrono::Utc;
 use tracing::{info, error, debug};
 
+/// Fields a `read_mask` on the user list RPCs can name.
+const USER_FIELDS: &[&str] = &["global_username", "created_at", "last_seen", "is_active"];
+
 pub struct UserServiceImpl {
-    user_repo: Arc<UserRepository>,
-    analysis_repo: Arc<PostgresUserAnalysisRepository>,
-    platform_identity_repo: Arc<PlatformIdentityRepository>,
+    user_repo: Arc<dyn UserRepo + Send + Sync>,
+    analysis_repo: Arc<dyn UserAnalysisRepository>,
+    platform_identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+    notes_repo: Arc<dyn UserNotesRepository>,
+    viewer_cards: Arc<ViewerCardService>,
+    recompute: Arc<AnalysisRecompute>,
 }
 
+/// Used when a list request does not set a limit.
+const DEFAULT_HISTORY_LIMIT: i64 = 50;
+/// Chat messages on a viewer card when the request does not say.
+const DEFAULT_CARD_MESSAGES: i64 = 10;
+
 impl UserServiceImpl {
     pub fn new(
-        user_repo: Arc<UserRepository>,
-        analysis_repo: Arc<PostgresUserAnalysisRepository>,
-        platform_identity_repo: Arc<PlatformIdentityRepository>,
+        user_repo: Arc<dyn UserRepo + Send + Sync>,
+        analysis_repo: Arc<dyn UserAnalysisRepository>,
+        platform_identity_repo: Arc<dyn PlatformIdentityRepo + Send + Sync>,
+        notes_repo: Arc<dyn UserNotesRepository>,
+        viewer_cards: Arc<ViewerCardService>,
+        recompute: Arc<AnalysisRecompute>,
     ) -> Self {
         Self {
             user_repo,
             analysis_repo,
             platform_identity_repo,
+            notes_repo,
+            viewer_cards,
+            recompute,
+        }
+    }
+
+    /// Who to credit for a note or action: the explicit name, else the calling token.
+    fn author_for<T>(request: &Request<T>, explicit: &str) -> String {
+        let explicit = explicit.trim();
+        if !explicit.is_empty() {
+            return explicit.to_string();
+        }
+        request.extensions().get::<Caller>()
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| "console".to_string())
+    }
+
+    fn list_limit(limit: i32) -> i64 {
+        if limit > 0 { (limit as i64).min(500) } else { DEFAULT_HISTORY_LIMIT }
+    }
+
+    fn user_note_to_proto(note: &UserNoteModel) -> UserNote {
+        UserNote {
+            note_id: note.note_id.to_string(),
+            user_id: note.user_id.to_string(),
+            author: note.author.clone(),
+            note_text: note.note_text.clone(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: note.created_at.timestamp(),
+                nanos: note.created_at.timestamp_subsec_nanos() as i32,
+            }),
+        }
+    }
+
+    fn moderation_action_to_proto(action: &ModerationActionModel) -> ModerationAction {
+        ModerationAction {
+            action_id: action.action_id.to_string(),
+            user_id: action.user_id.to_string(),
+            platform: action.platform.clone(),
+            channel: action.channel.clone(),
+            action_type: action.action_type.to_string(),
+            reason: action.reason.clone().unwrap_or_default(),
+            duration_seconds: action.duration_seconds.unwrap_or_default(),
+            moderator: action.moderator.clone(),
+            category: action.category.clone(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: action.created_at.timestamp(),
+                nanos: action.created_at.timestamp_subsec_nanos() as i32,
+            }),
         }
     }
     
@@ -63,16 +127,42 @@ impl UserServiceImpl {
         }
     }
     
+    /// `user_to_proto` with only the fields in `mask` filled in.
+    fn masked_user(user: &user_models::User, mask: &ReadMask) -> User {
+        let mut proto = Self::user_to_proto(user);
+        if !mask.includes("global_username") { proto.global_username.clear(); }
+        if !mask.includes("created_at") { proto.created_at = None; }
+        if !mask.includes("last_seen") { proto.last_seen = None; }
+        if !mask.includes("is_active") { proto.is_active = false; }
+        proto
+    }
+
+    fn proto_to_platform(platform: i32) -> Option<maowbot_common::models::platform::Platform> {
+        use maowbot_common::models::platform::Platform as Model;
+        match Platform::try_from(platform) {
+            Ok(Platform::TwitchIrc) => Some(Model::TwitchIRC),
+            Ok(Platform::TwitchEventsub) => Some(Model::TwitchEventSub),
+            Ok(Platform::Discord) => Some(Model::Discord),
+            Ok(Platform::Vrchat) => Some(Model::VRChat),
+            Ok(Platform::Kick) => Some(Model::Kick),
+            Ok(Platform::TwitchHelix) => Some(Model::Twitch),
+            Ok(Platform::Obs) => Some(Model::OBS),
+            _ => None,
+        }
+    }
+
     // Helper to convert platform identity
     fn platform_identity_to_proto(identity: &PlatformIdentityModel) -> maowbot_proto::maowbot::common::PlatformIdentity {
         maowbot_proto::maowbot::common::PlatformIdentity {
             platform_identity_id: identity.platform_identity_id.to_string(),
             user_id: identity.user_id.to_string(),
             platform: match identity.platform {
+                maowbot_common::models::platform::Platform::Twitch => Platform::TwitchHelix as i32,
                 maowbot_common::models::platform::Platform::TwitchIRC => Platform::TwitchIrc as i32,
                 maowbot_common::models::platform::Platform::TwitchEventSub => Platform::TwitchEventsub as i32,
                 maowbot_common::models::platform::Platform::Discord => Platform::Discord as i32,
                 maowbot_common::models::platform::Platform::VRChat => Platform::Vrchat as i32,
+                maowbot_common::models::platform::Platform::Kick => Platform::Kick as i32,
                 _ => Platform::Unknown as i32,
             },
             platform_user_id: identity.platform_user_id.clone(),
@@ -110,6 +200,39 @@ impl UserServiceImpl {
                 seconds: analysis.updated_at.timestamp(),
                 nanos: analysis.updated_at.timestamp_subsec_nanos() as i32,
             }),
+            bot_score: analysis.bot_score,
+            bot_signals: analysis.bot_signals.clone(),
+            bot_scored_at: analysis.bot_scored_at.map(|t| prost_types::Timestamp {
+                seconds: t.timestamp(),
+                nanos: t.timestamp_subsec_nanos() as i32,
+            }),
+        }
+    }
+
+    fn recompute_run_to_proto(run: &RecomputeRun) -> AnalysisRecomputeRun {
+        let to_ts = |t: chrono::DateTime<Utc>| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        };
+        AnalysisRecomputeRun {
+            run_id: run.run_id.to_string(),
+            since: Some(to_ts(run.since)),
+            until: Some(to_ts(run.until)),
+            status: run.status.clone(),
+            total_users: run.total_users,
+            processed_users: run.processed_users,
+            error: run.error.clone().unwrap_or_default(),
+            started_at: Some(to_ts(run.started_at)),
+            updated_at: Some(to_ts(run.updated_at)),
+            finished_at: run.finished_at.map(to_ts),
+        }
+    }
+
+    fn recompute_error(e: maowbot_common::error::Error) -> Status {
+        match e {
+            maowbot_common::error::Error::NotFound(msg) => Status::not_found(msg),
+            maowbot_common::error::Error::ValidationError(msg) => Status::failed_precondition(msg),
+            e => Status::internal(format!("Analysis recompute failed: {}", e)),
         }
     }
 }
@@ -279,28 +402,41 @@ impl UserService for UserServiceImpl {
     ) -> Result<Response<ListUsersResponse>, Status> {
         let req = request.into_inner();
         debug!("Listing users");
-        
-        // For now, return all users that are active
-        // TODO: Implement proper pagination and filtering
-        let all_users = self.user_repo
-            .list_all()
+
+        let page = Page::from_request(req.page.as_ref())?;
+        let mask = ReadMask::parse(req.read_mask.as_ref(), USER_FIELDS)?;
+        let sort = match sort_field(&req.order_by, &["created_at", "last_seen", "username"])? {
+            "last_seen" => UserSort::LastSeen,
+            "username" => UserSort::Username,
+            _ => UserSort::CreatedAt,
+        };
+        let filter = req.filter.unwrap_or_default();
+        let query = UserListQuery {
+            active_only: filter.active_only,
+            platforms: filter.platforms.iter()
+                .filter_map(|&p| Self::proto_to_platform(p))
+                .map(|p| p.to_string())
+                .collect(),
+            roles: filter.roles,
+            name_prefix: Some(filter.name_prefix).filter(|p| !p.is_empty()),
+            sort,
+            descending: req.descending,
+            offset: page.offset as i64,
+            limit: page.size as i64,
+        };
+
+        let (users, total) = self.user_repo
+            .list_page(&query)
             .await
             .map_err(|e| Status::internal(format!("Failed to list users: {}", e)))?;
-        
-        let users: Vec<user_models::User> = all_users.into_iter()
-            .filter(|u| u.is_active)
-            .collect();
-        
-        let proto_users: Vec<User> = users.into_iter()
-            .map(|u| Self::user_to_proto(&u))
+
+        let proto_users: Vec<User> = users.iter()
+            .map(|u| Self::masked_user(u, &mask))
             .collect();
-        
+
         Ok(Response::new(ListUsersResponse {
+            page: Some(page.response(proto_users.len(), total.max(0) as usize)),
             users: proto_users,
-            page: Some(PageResponse {
-                next_page_token: String::new(),
-                total_count: 0, // TODO: Get actual count
-            }),
         }))
     }
     
@@ -310,7 +446,10 @@ impl UserService for UserServiceImpl {
     ) -> Result<Response<SearchUsersResponse>, Status> {
         let req = request.into_inner();
         info!("Searching users with query: {}", req.query);
-        
+
+        let page = Page::from_request(req.page.as_ref())?;
+        let mask = ReadMask::parse(req.read_mask.as_ref(), USER_FIELDS)?;
+
         // Simple search by username - using list_all and filtering
         let all_users = self.user_repo
             .list_all()
@@ -318,15 +457,18 @@ impl UserService for UserServiceImpl {
             .map_err(|e| Status::internal(format!("Failed to search users: {}", e)))?;
         
         let query_lower = req.query.to_lowercase();
-        let users: Vec<user_models::User> = all_users.into_iter()
+        let mut users: Vec<user_models::User> = all_users.into_iter()
             .filter(|u| u.global_username.as_ref()
                 .map(|n| n.to_lowercase().contains(&query_lower))
                 .unwrap_or(false))
             .collect();
-        
-        let results: Vec<UserSearchResult> = users.into_iter()
+        // Stable order so page tokens stay valid between calls
+        users.sort_by(|a, b| a.global_username.cmp(&b.global_username).then(a.user_id.cmp(&b.user_id)));
+        let (users, page) = page.slice(users);
+
+        let results: Vec<UserSearchResult> = users.iter()
             .map(|u| UserSearchResult {
-                user: Some(Self::user_to_proto(&u)),
+                user: Some(Self::masked_user(u, &mask)),
                 matched_identities: vec![],
                 relevance_score: 1.0, // TODO: Calculate actual relevance
             })
@@ -334,10 +476,7 @@ impl UserService for UserServiceImpl {
         
         Ok(Response::new(SearchUsersResponse {
             results,
-            page: Some(PageResponse {
-                next_page_token: String::new(),
-                total_count: 0,
-            }),
+            page: Some(page),
         }))
     }
     
@@ -439,7 +578,7 @@ impl UserService for UserServiceImpl {
             .map_err(|e| Status::invalid_argument(format!("Invalid target_user_id: {}", e)))?;
         
         // Verify both users exist
-        let from_user = self.user_repo.get(from_user_id).await
+        let _from_user = self.user_repo.get(from_user_id).await
             .map_err(|e| Status::internal(format!("Failed to get from_user: {}", e)))?
             .ok_or_else(|| Status::not_found("From user not found"))?;
         
@@ -463,6 +602,7 @@ impl UserService for UserServiceImpl {
                 merged_identities += 1;
             }
         }
+        info!("Moved {} identities from {} to {}", merged_identities, from_user_id, to_user_id);
         
         // Delete the source user based on strategy
         if req.strategy == MergeStrategy::KeepTarget as i32 {
@@ -511,6 +651,7 @@ impl UserService for UserServiceImpl {
                         maowbot_common::models::platform::Platform::TwitchEventSub => Platform::TwitchEventsub,
                         maowbot_common::models::platform::Platform::Discord => Platform::Discord,
                         maowbot_common::models::platform::Platform::VRChat => Platform::Vrchat,
+                        maowbot_common::models::platform::Platform::Kick => Platform::Kick,
                         _ => Platform::Unknown,
                     };
                     requested_platforms.contains(&proto_platform)
@@ -549,6 +690,7 @@ impl UserService for UserServiceImpl {
             Ok(Platform::TwitchEventsub) => maowbot_common::models::platform::Platform::TwitchEventSub,
             Ok(Platform::Discord) => maowbot_common::models::platform::Platform::Discord,
             Ok(Platform::Vrchat) => maowbot_common::models::platform::Platform::VRChat,
+            Ok(Platform::Kick) => maowbot_common::models::platform::Platform::Kick,
             _ => return Err(Status::invalid_argument("Invalid platform")),
         };
         
@@ -749,6 +891,9 @@ impl UserService for UserServiceImpl {
                     horni_score: 0.0,
                     ai_notes: None,
                     moderator_notes: None,
+                    bot_score: 0.0,
+                    bot_signals: Vec::new(),
+                    bot_scored_at: None,
                     created_at: Utc::now(),
                     updated_at: Utc::now(),
                 }
@@ -825,6 +970,9 @@ impl UserService for UserServiceImpl {
                     horni_score: 0.0,
                     ai_notes: None,
                     moderator_notes: Some(req.note_text.clone()),
+                    bot_score: 0.0,
+                    bot_signals: Vec::new(),
+                    bot_scored_at: None,
                     created_at: Utc::now(),
                     updated_at: Utc::now(),
                 };
@@ -853,6 +1001,191 @@ impl UserService for UserServiceImpl {
         Ok(Response::new(()))
     }
     
+    async fn add_user_note(
+        &self,
+        request: Request<AddUserNoteRequest>,
+    ) -> Result<Response<AddUserNoteResponse>, Status> {
+        let author = Self::author_for(&request, &request.get_ref().author);
+        let req = request.into_inner();
+
+        let user_id = Uuid::parse_str(&req.user_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid user_id: {}", e)))?;
+        if req.note_text.trim().is_empty() {
+            return Err(Status::invalid_argument("note_text is required"));
+        }
+
+        let note = UserNoteModel::new(user_id, &author, req.note_text.trim());
+        self.notes_repo.add_note(&note).await
+            .map_err(|e| Status::internal(format!("Failed to add note: {}", e)))?;
+        info!("Note added to user {} by '{}'", user_id, author);
+
+        Ok(Response::new(AddUserNoteResponse {
+            note: Some(Self::user_note_to_proto(&note)),
+        }))
+    }
+
+    async fn list_user_notes(
+        &self,
+        request: Request<ListUserNotesRequest>,
+    ) -> Result<Response<ListUserNotesResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = Uuid::parse_str(&req.user_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid user_id: {}", e)))?;
+
+        let notes = self.notes_repo.list_notes(user_id, Self::list_limit(req.limit)).await
+            .map_err(|e| Status::internal(format!("Failed to list notes: {}", e)))?;
+
+        Ok(Response::new(ListUserNotesResponse {
+            notes: notes.iter().map(Self::user_note_to_proto).collect(),
+        }))
+    }
+
+    async fn delete_user_note(
+        &self,
+        request: Request<DeleteUserNoteRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = request.into_inner();
+        let note_id = Uuid::parse_str(&req.note_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid note_id: {}", e)))?;
+
+        let deleted = self.notes_repo.delete_note(note_id).await
+            .map_err(|e| Status::internal(format!("Failed to delete note: {}", e)))?;
+        if !deleted {
+            return Err(Status::not_found(format!("Note {} not found", note_id)));
+        }
+        Ok(Response::new(()))
+    }
+
+    async fn record_moderation_action(
+        &self,
+        request: Request<RecordModerationActionRequest>,
+    ) -> Result<Response<RecordModerationActionResponse>, Status> {
+        let moderator = Self::author_for(&request, &request.get_ref().moderator);
+        let req = request.into_inner();
+
+        let user_id = Uuid::parse_str(&req.user_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid user_id: {}", e)))?;
+        let action_type = ModerationActionType::from_str(&req.action_type)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        if req.platform.trim().is_empty() {
+            return Err(Status::invalid_argument("platform is required"));
+        }
+
+        let mut action = ModerationActionModel::new(
+            user_id,
+            &req.platform.trim().to_lowercase(),
+            req.channel.trim(),
+            action_type,
+            &moderator,
+        );
+        action.reason = Some(req.reason.trim().to_string()).filter(|r| !r.is_empty());
+        if action_type == ModerationActionType::Timeout && req.duration_seconds > 0 {
+            action.duration_seconds = Some(req.duration_seconds);
+        }
+
+        self.notes_repo.record_action(&action).await
+            .map_err(|e| Status::internal(format!("Failed to record moderation action: {}", e)))?;
+        info!("Recorded {} for user {} on {} by '{}'", action.action_type, user_id, action.platform, moderator);
+
+        Ok(Response::new(RecordModerationActionResponse {
+            action: Some(Self::moderation_action_to_proto(&action)),
+        }))
+    }
+
+    async fn list_moderation_history(
+        &self,
+        request: Request<ListModerationHistoryRequest>,
+    ) -> Result<Response<ListModerationHistoryResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = Uuid::parse_str(&req.user_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid user_id: {}", e)))?;
+
+        let actions = self.notes_repo.list_actions(user_id, Self::list_limit(req.limit)).await
+            .map_err(|e| Status::internal(format!("Failed to list moderation history: {}", e)))?;
+
+        Ok(Response::new(ListModerationHistoryResponse {
+            actions: actions.iter().map(Self::moderation_action_to_proto).collect(),
+        }))
+    }
+
+    async fn get_viewer_card(
+        &self,
+        request: Request<GetViewerCardRequest>,
+    ) -> Result<Response<GetViewerCardResponse>, Status> {
+        let req = request.into_inner();
+        let limit = if req.message_limit > 0 { (req.message_limit as i64).min(100) } else { DEFAULT_CARD_MESSAGES };
+        let card = self.viewer_cards.card(&req.user, limit).await
+            .map_err(|e| match e {
+                maowbot_common::error::Error::NotFound(msg) => Status::not_found(msg),
+                e => Status::internal(format!("Failed to build viewer card: {}", e)),
+            })?;
+
+        let to_ts = |t: chrono::DateTime<Utc>| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        };
+        let has_twitch_status = card.twitch.is_some();
+        let twitch = card.twitch.unwrap_or_default();
+        Ok(Response::new(GetViewerCardResponse {
+            user: Some(Self::user_to_proto(&card.user)),
+            identities: card.identities.iter().map(Self::platform_identity_to_proto).collect(),
+            has_twitch_status,
+            followed_at: twitch.followed_at.map(to_ts),
+            sub_tier: twitch.sub_tier.unwrap_or_default(),
+            sub_is_gift: twitch.sub_is_gift,
+            redeems_used: card.redeems_used as i32,
+            points_spent: card.points_spent,
+            notes: card.notes.iter().map(Self::user_note_to_proto).collect(),
+            recent_messages: card.recent_messages.into_iter().map(|m| ViewerCardMessage {
+                platform: m.platform,
+                channel: m.channel,
+                text: m.message_text,
+                timestamp: Some(to_ts(m.timestamp)),
+            }).collect(),
+            analysis: card.analysis.as_ref().map(Self::user_analysis_to_proto),
+            ai_memory_count: card.ai_memory.memory_count as i32,
+            last_ai_memory_at: card.ai_memory.last_memory_at.map(to_ts),
+            ai_memory_recent: card.ai_memory.recent,
+            ai_notes: card.ai_memory.ai_notes.unwrap_or_default(),
+            warnings: card.warnings,
+        }))
+    }
+
+    async fn start_analysis_recompute(
+        &self,
+        request: Request<StartAnalysisRecomputeRequest>,
+    ) -> Result<Response<AnalysisRecomputeRun>, Status> {
+        let req = request.into_inner();
+        if req.days < 0 {
+            return Err(Status::invalid_argument("days must not be negative"));
+        }
+        let run = if req.resume {
+            self.recompute.resume().await
+        } else {
+            self.recompute.start((req.days > 0).then_some(req.days as i64)).await
+        }.map_err(Self::recompute_error)?;
+        info!("User analysis run {} started ({} users)", run.run_id, run.total_users);
+        Ok(Response::new(Self::recompute_run_to_proto(&run)))
+    }
+
+    async fn get_analysis_recompute_status(
+        &self,
+        _request: Request<GetAnalysisRecomputeStatusRequest>,
+    ) -> Result<Response<GetAnalysisRecomputeStatusResponse>, Status> {
+        let run = self.recompute.latest().await.map_err(Self::recompute_error)?;
+        Ok(Response::new(GetAnalysisRecomputeStatusResponse {
+            run: run.as_ref().map(Self::recompute_run_to_proto),
+        }))
+    }
+
+    async fn cancel_analysis_recompute(
+        &self,
+        _request: Request<CancelAnalysisRecomputeRequest>,
+    ) -> Result<Response<AnalysisRecomputeRun>, Status> {
+        let run = self.recompute.cancel().await.map_err(Self::recompute_error)?;
+        Ok(Response::new(Self::recompute_run_to_proto(&run)))
+    }
+
     type StreamUserUpdatesStream = tonic::codec::Streaming<UserUpdateEvent>;
     
     async fn stream_user_updates(
@@ -1,26 +1,62 @@
 use tonic::{Request, Response, Status};
 use maowbot_proto::maowbot::services::{vr_chat_service_server::VrChatService, *};
-use maowbot_proto::maowbot::common;
 use maowbot_core::plugins::manager::PluginManager;
+use maowbot_core::platforms::vrchat::client::SessionState;
+use maowbot_core::services::vrchat_session_service::{VRChatSessionService, VRChatSessionStatus};
+use maowbot_core::services::vrchat_group_service::VRChatGroupService;
+use maowbot_common::error::Error;
 use maowbot_common::traits::api::VrchatApi;
 use std::sync::Arc;
 use chrono::Utc;
-use tracing::{info, error, debug};
+use tracing::{info, debug};
 use prost_types;
-use uuid::Uuid;
+use super::convert::to_timestamp;
 
 pub struct VRChatServiceImpl {
     plugin_manager: Arc<PluginManager>,
+    session_service: Arc<VRChatSessionService>,
+    group_service: Arc<VRChatGroupService>,
 }
 
 impl VRChatServiceImpl {
-    pub fn new(plugin_manager: Arc<PluginManager>) -> Self {
+    pub fn new(
+        plugin_manager: Arc<PluginManager>,
+        session_service: Arc<VRChatSessionService>,
+        group_service: Arc<VRChatGroupService>,
+    ) -> Self {
         Self {
             plugin_manager,
+            session_service,
+            group_service,
         }
     }
 }
 
+fn group_error(what: &str, e: Error) -> Status {
+    match e {
+        Error::NotFound(msg) => Status::not_found(msg),
+        Error::Parse(msg) => Status::invalid_argument(msg),
+        e => Status::internal(format!("Failed to {}: {}", what, e)),
+    }
+}
+
+fn session_to_proto(status: VRChatSessionStatus) -> VrChatSession {
+    VrChatSession {
+        user_id: status.user_id.to_string(),
+        user_name: status.user_name,
+        state: match status.state {
+            Some(SessionState::Valid) => "valid",
+            Some(SessionState::TwoFactorRequired) => "2fa_required",
+            Some(SessionState::Expired) => "expired",
+            None => "unknown",
+        }.to_string(),
+        display_name: status.display_name.unwrap_or_default(),
+        expires_at: status.expires_at.map(to_timestamp),
+        checked_at: Some(to_timestamp(status.checked_at)),
+        error: status.error.unwrap_or_default(),
+    }
+}
+
 #[tonic::async_trait]
 impl VrChatService for VRChatServiceImpl {
     async fn get_current_user(&self, request: Request<GetCurrentUserRequest>) -> Result<Response<GetCurrentUserResponse>, Status> {
@@ -381,4 +417,73 @@ impl VrChatService for VRChatServiceImpl {
     async fn stream_vr_chat_events(&self, _: Request<StreamVrChatEventsRequest>) -> Result<Response<Self::StreamVRChatEventsStream>, Status> {
         Err(Status::unimplemented("Not implemented"))
     }
+    async fn list_vr_chat_sessions(&self, _: Request<ListVrChatSessionsRequest>) -> Result<Response<ListVrChatSessionsResponse>, Status> {
+        let sessions = self.session_service.statuses().into_iter().map(session_to_proto).collect();
+        Ok(Response::new(ListVrChatSessionsResponse { sessions }))
+    }
+    async fn check_vr_chat_sessions(&self, _: Request<CheckVrChatSessionsRequest>) -> Result<Response<ListVrChatSessionsResponse>, Status> {
+        let sessions = self.session_service.check_all().await
+            .map_err(|e| Status::internal(format!("Failed to check VRChat sessions: {}", e)))?
+            .into_iter()
+            .map(session_to_proto)
+            .collect();
+        Ok(Response::new(ListVrChatSessionsResponse { sessions }))
+    }
+    async fn get_vr_chat_presence(&self, request: Request<GetVrChatPresenceRequest>) -> Result<Response<GetVrChatPresenceResponse>, Status> {
+        let req = request.into_inner();
+        let service = self.plugin_manager.vrchat_presence_service.clone()
+            .ok_or_else(|| Status::unavailable("VRChat presence tracking is not running"))?;
+        let snapshot = if req.refresh {
+            service.poll().await
+                .map_err(|e| Status::internal(format!("Failed to check VRChat presence: {}", e)))?
+        } else {
+            service.snapshot()
+        };
+        Ok(Response::new(GetVrChatPresenceResponse {
+            location: snapshot.location.unwrap_or_default(),
+            world_name: snapshot.world_name.unwrap_or_default(),
+            friends: snapshot.friends.into_iter().map(|f| VrChatPresentFriend {
+                user_id: f.user_id,
+                display_name: f.display_name,
+                since: Some(to_timestamp(f.since)),
+                watched: f.watched,
+            }).collect(),
+            checked_at: snapshot.checked_at.map(to_timestamp),
+        }))
+    }
+    async fn list_vr_chat_group_requests(&self, request: Request<ListVrChatGroupRequestsRequest>) -> Result<Response<ListVrChatGroupRequestsResponse>, Status> {
+        let req = request.into_inner();
+        let snapshot = if req.refresh {
+            self.group_service.poll().await
+                .map_err(|e| group_error("check VRChat group requests", e))?
+        } else {
+            self.group_service.snapshot()
+        };
+        Ok(Response::new(ListVrChatGroupRequestsResponse {
+            group_id: snapshot.group_id.unwrap_or_default(),
+            group_name: snapshot.group_name.unwrap_or_default(),
+            requests: snapshot.requests.into_iter().map(|r| VrChatGroupJoinRequest {
+                user_id: r.user_id,
+                display_name: r.display_name,
+                requested_at: r.requested_at.map(to_timestamp),
+            }).collect(),
+            checked_at: snapshot.checked_at.map(to_timestamp),
+        }))
+    }
+    async fn respond_vr_chat_group_request(&self, request: Request<RespondVrChatGroupRequestRequest>) -> Result<Response<RespondVrChatGroupRequestResponse>, Status> {
+        let req = request.into_inner();
+        info!("{} VRChat group join request from '{}'", if req.accept { "Approving" } else { "Rejecting" }, req.user);
+        let handled = self.group_service.respond(&req.user, req.accept).await
+            .map_err(|e| group_error("answer the join request", e))?;
+        Ok(Response::new(RespondVrChatGroupRequestResponse {
+            user_id: handled.user_id,
+            display_name: handled.display_name,
+        }))
+    }
+    async fn post_vr_chat_group_announcement(&self, request: Request<PostVrChatGroupAnnouncementRequest>) -> Result<Response<PostVrChatGroupAnnouncementResponse>, Status> {
+        let req = request.into_inner();
+        let post_id = self.group_service.post(&req.title, &req.text, req.notify).await
+            .map_err(|e| group_error("post to the VRChat group", e))?;
+        Ok(Response::new(PostVrChatGroupAnnouncementResponse { post_id }))
+    }
 }
\ No newline at end of file
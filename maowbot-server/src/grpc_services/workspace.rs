@@ -0,0 +1,120 @@
+// Workspace selection for gRPC requests.
+//
+// Clients pick a workspace with the `x-maowbot-workspace` metadata header,
+// holding either the workspace name or its UUID. Requests without it use the
+// default workspace, so single-broadcaster setups need no changes. A token
+// limited to one workspace gets that workspace without the header, and is
+// refused any other. The resolved workspace is what the audit log records.
+
+use std::sync::Arc;
+use tonic::{Request, Status};
+use uuid::Uuid;
+use maowbot_common::models::workspace::DEFAULT_WORKSPACE_ID;
+use maowbot_common::traits::repository_traits::WorkspaceRepository;
+use maowbot_proto::WORKSPACE_METADATA_KEY;
+
+use crate::authz::{AuditNote, Caller};
+
+#[derive(Clone)]
+pub struct WorkspaceResolver {
+    repo: Arc<dyn WorkspaceRepository>,
+}
+
+impl WorkspaceResolver {
+    pub fn new(repo: Arc<dyn WorkspaceRepository>) -> Self {
+        Self { repo }
+    }
+
+    pub fn repo(&self) -> &Arc<dyn WorkspaceRepository> {
+        &self.repo
+    }
+
+    /// The workspace selected by `request`'s metadata, checked against the
+    /// caller's token.
+    pub async fn resolve<T>(&self, request: &Request<T>) -> Result<Uuid, Status> {
+        let allowed = request.extensions().get::<Caller>().and_then(|c| c.workspace_id);
+        let selected = self.selected(request).await?;
+        let workspace_id = match (allowed, selected) {
+            (Some(allowed), Some(selected)) if allowed != selected => return Err(Status::permission_denied(
+                "This API token is limited to another workspace",
+            )),
+            (Some(allowed), _) => allowed,
+            (None, selected) => selected.unwrap_or(DEFAULT_WORKSPACE_ID),
+        };
+        AuditNote::of(request).workspace(workspace_id);
+        Ok(workspace_id)
+    }
+
+    async fn selected<T>(&self, request: &Request<T>) -> Result<Option<Uuid>, Status> {
+        let Some(value) = request.metadata().get(WORKSPACE_METADATA_KEY) else {
+            return Ok(None);
+        };
+        let selector = value.to_str()
+            .map_err(|_| Status::invalid_argument(format!("Invalid {} header", WORKSPACE_METADATA_KEY)))?
+            .trim();
+        if selector.is_empty() {
+            return Ok(None);
+        }
+
+        let found = match Uuid::parse_str(selector) {
+            Ok(id) => self.repo.get_workspace(id).await,
+            Err(_) => self.repo.get_workspace_by_name(selector).await,
+        }
+        .map_err(|e| Status::internal(format!("Failed to look up workspace: {}", e)))?;
+
+        found
+            .map(|ws| Some(ws.workspace_id))
+            .ok_or_else(|| Status::not_found(format!("Workspace '{}' not found", selector)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use maowbot_common::models::api_token::ApiRole;
+    use maowbot_common::models::workspace::Workspace;
+    use maowbot_core::crypto::Encryptor;
+    use maowbot_core::repositories::{memory, Repositories};
+
+    fn request(caller_workspace: Option<Uuid>, header: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        request.extensions_mut().insert(Caller {
+            name: "brand-mod".to_string(),
+            role: ApiRole::Moderator,
+            workspace_id: caller_workspace,
+        });
+        if let Some(header) = header {
+            request.metadata_mut().insert(WORKSPACE_METADATA_KEY, header.parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_scoped_tokens_stay_in_their_workspace() {
+        let db = memory::open_database().await.unwrap();
+        let repos = Repositories::memory(&db, Encryptor::new(&[7u8; 32]).unwrap());
+        let brand = Workspace {
+            workspace_id: Uuid::new_v4(),
+            name: "brand".to_string(),
+            display_name: None,
+            created_at: Utc::now(),
+        };
+        repos.workspaces.create_workspace(&brand).await.unwrap();
+        let resolver = WorkspaceResolver::new(repos.workspaces.clone());
+
+        // Unscoped tokens pick any workspace, or the default one
+        assert_eq!(resolver.resolve(&request(None, None)).await.unwrap(), DEFAULT_WORKSPACE_ID);
+        assert_eq!(resolver.resolve(&request(None, Some("brand"))).await.unwrap(), brand.workspace_id);
+
+        // Scoped tokens get theirs without asking, and nothing else
+        let scoped = Some(brand.workspace_id);
+        assert_eq!(resolver.resolve(&request(scoped, None)).await.unwrap(), brand.workspace_id);
+        assert_eq!(resolver.resolve(&request(scoped, Some("brand"))).await.unwrap(), brand.workspace_id);
+        let denied = resolver
+            .resolve(&request(scoped, Some(&DEFAULT_WORKSPACE_ID.to_string())))
+            .await
+            .unwrap_err();
+        assert_eq!(denied.code(), tonic::Code::PermissionDenied);
+    }
+}
@@ -0,0 +1,243 @@
+//! maowbot-server/src/logging.rs
+//!
+//! Tracing setup: plain-text or JSON-lines output, with a reloadable filter so
+//! per-module levels can be changed at runtime over gRPC.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("Unknown log format '{}' (expected text or json)", other)),
+        }
+    }
+}
+
+/// Runtime handle on the active tracing filter.
+pub struct LogControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    format: LogFormat,
+    state: Mutex<LogLevels>,
+}
+
+/// The filter as a base directive string plus per-module overrides.
+#[derive(Debug, Clone, Default)]
+pub struct LogLevels {
+    /// From RUST_LOG, or built from --log-level
+    pub base: String,
+    /// module path => level
+    pub overrides: BTreeMap<String, String>,
+}
+
+impl LogLevels {
+    /// The EnvFilter directive string; overrides come last so they win.
+    pub fn filter_string(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if !self.base.is_empty() {
+            parts.push(self.base.clone());
+        }
+        parts.extend(self.overrides.iter().map(|(module, level)| format!("{}={}", module, level)));
+        parts.join(",")
+    }
+}
+
+static LOG_CONTROL: OnceLock<LogControl> = OnceLock::new();
+
+/// The control handle, once `init_tracing` has run.
+pub fn log_control() -> Option<&'static LogControl> {
+    LOG_CONTROL.get()
+}
+
+/// Checks a module path and level, normalizing the level to lowercase.
+pub fn validate_directive(module: &str, level: &str) -> Result<String, String> {
+    if module.is_empty()
+        || !module.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '-')
+    {
+        return Err(format!("Invalid module path '{}'", module));
+    }
+    let level = level.to_lowercase();
+    level.parse::<LevelFilter>()
+        .map_err(|_| format!("Invalid level '{}' (expected off, error, warn, info, debug or trace)", level))?;
+    Ok(level)
+}
+
+impl LogControl {
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    pub fn levels(&self) -> LogLevels {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Sets the level for one module (e.g. `maowbot_core::platforms`) and applies it immediately.
+    pub fn set_module_level(&self, module: &str, level: &str) -> Result<LogLevels, String> {
+        let level = validate_directive(module, level)?;
+        self.update(|levels| {
+            levels.overrides.insert(module.to_string(), level);
+        })
+    }
+
+    /// Drops a module override so it falls back to the base filter.
+    pub fn reset_module_level(&self, module: &str) -> Result<LogLevels, String> {
+        self.update(|levels| {
+            levels.overrides.remove(module);
+        })
+    }
+
+    fn update(&self, change: impl FnOnce(&mut LogLevels)) -> Result<LogLevels, String> {
+        let mut state = self.state.lock().unwrap();
+        let mut next = state.clone();
+        change(&mut next);
+        let filter = EnvFilter::try_new(next.filter_string())
+            .map_err(|e| format!("Invalid filter: {}", e))?;
+        self.handle.reload(filter).map_err(|e| format!("Failed to apply filter: {}", e))?;
+        *state = next.clone();
+        Ok(next)
+    }
+}
+
+pub fn init_tracing(level: &str, format: LogFormat) {
+    let base = std::env::var("RUST_LOG")
+        .ok()
+        .filter(|v| EnvFilter::try_new(v).is_ok())
+        .unwrap_or_else(|| format!("maowbot={0},twitch_irc={0}", level));
+    let filter = EnvFilter::new(&base);
+    let (filter_layer, handle) = reload::Layer::new(filter);
+    let registry = tracing_subscriber::registry().with(filter_layer);
+
+    let result = match format {
+        LogFormat::Text => tracing::subscriber::set_global_default(
+            registry.with(tracing_subscriber::fmt::layer()),
+        ),
+        LogFormat::Json => tracing::subscriber::set_global_default(
+            registry.with(tracing_subscriber::fmt::layer().event_format(JsonFormat)),
+        ),
+    };
+    result.expect("Failed to set global subscriber");
+    tracing_log::LogTracer::init().ok();
+
+    let _ = LOG_CONTROL.set(LogControl {
+        handle,
+        format,
+        state: Mutex::new(LogLevels { base, overrides: BTreeMap::new() }),
+    });
+}
+
+/// One JSON object per line: timestamp, level, target, message, the enclosing
+/// spans, then every field on the event (event_type, platform, user, latency_ms, ...).
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut record = Map::new();
+        record.insert("timestamp".into(), Value::String(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)));
+        record.insert("level".into(), Value::String(meta.level().to_string()));
+        record.insert("target".into(), Value::String(meta.target().to_string()));
+
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope
+                .from_root()
+                .map(|span| Value::String(span.name().to_string()))
+                .collect();
+            if !spans.is_empty() {
+                record.insert("spans".into(), Value::Array(spans));
+            }
+        }
+
+        let mut visitor = JsonVisitor(&mut record);
+        event.record(&mut visitor);
+
+        let line = serde_json::to_string(&Value::Object(record)).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), serde_json::json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), Value::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), Value::String(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().into(), Value::String(format!("{:?}", value)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_string_puts_overrides_last() {
+        let mut levels = LogLevels { base: "maowbot=info".into(), overrides: BTreeMap::new() };
+        levels.overrides.insert("maowbot_core::platforms".into(), "trace".into());
+        assert_eq!(levels.filter_string(), "maowbot=info,maowbot_core::platforms=trace");
+    }
+
+    #[test]
+    fn test_validate_directive() {
+        assert_eq!(validate_directive("maowbot_core", "DEBUG").unwrap(), "debug");
+        assert!(validate_directive("maowbot_core", "loud").is_err());
+        assert!(validate_directive("a=b", "info").is_err());
+        assert!(validate_directive("", "info").is_err());
+    }
+}
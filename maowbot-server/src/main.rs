@@ -2,16 +2,18 @@
 //!
 //! Entry point: parse CLI args, initialize tracing, then call run_server or run_client.
 
+// The gRPC services and their helpers fail with tonic's `Status`, which is large
+#![allow(clippy::result_large_err)]
+
 use clap::Parser;
 use std::error::Error as StdError;
 use tracing::{info, error};
-use tracing_subscriber::{EnvFilter};
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "maowbot")]
 #[command(author, version, about = "MaowBot - multi‑platform streaming bot with plugin system")]
 pub struct Args {
-    /// Mode: "server" or "client"
+    /// Mode: "server", "client", "migrate-db" or "service" (Windows service manager only)
     #[arg(long, default_value = "server")]
     pub mode: String,
 
@@ -19,10 +21,25 @@ pub struct Args {
     #[arg(long, default_value = "0.0.0.0:9999")]
     pub server_addr: String,
 
-    /// Postgres connection URL.
-    #[arg(long, default_value = "postgres://maow@localhost:5432/maowbot")]
+    /// Database URL: postgres://... or sqlite://path/to/maowbot.db.
+    /// The server and --mode migrate-db run on either.
+    #[arg(long, visible_alias = "db", default_value = "postgres://maow@localhost:5432/maowbot")]
     pub db_path: String,
 
+    /// With --mode migrate-db: the database URL to copy --db's data into.
+    #[arg(long)]
+    pub migrate_to: Option<String>,
+
+    /// With --mode migrate-db: copy even though some data (history, usage and
+    /// device settings) isn't migrated; the run lists what's left behind.
+    #[arg(long)]
+    pub partial: bool,
+
+    /// With --mode server: run on in-memory repositories instead of --db.
+    /// Nothing is saved when the server stops.
+    #[arg(long, default_value = "false")]
+    pub memory_db: bool,
+
     /// Passphrase for plugin connections
     #[arg(long)]
     pub plugin_passphrase: Option<String>,
@@ -49,24 +66,29 @@ pub struct Args {
     /// Logging level: "info", "warn", "debug", "error", or "trace"
     #[arg(long = "log-level", short = 'L', default_value = "info", value_parser = ["info", "warn", "debug", "error", "trace"])]
     pub log_level: String,
-}
 
-fn init_tracing(level: &str) {
-    let default_filter = format!("maowbot={0},twitch_irc={0}", level);
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new(default_filter));
-    let subscriber = tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .finish();
-    tracing::subscriber::set_global_default(subscriber)
-        .expect("Failed to set global subscriber");
-    tracing_log::LogTracer::init().ok();
+    /// Log output: "text" for humans, "json" for one structured object per line
+    #[arg(long = "log-format", default_value = "text", value_parser = ["text", "json"])]
+    pub log_format: String,
+
+    /// Directory to run in; certs/, postgres/ and the restart state are relative to it.
+    /// Set by `system install-service`, since services don't start in the install directory.
+    #[arg(long)]
+    pub work_dir: Option<String>,
+
+    /// With --mode client: wait up to SECS (default 120) for the server to finish starting
+    #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "120")]
+    pub wait_ready: Option<u64>,
 }
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 4)]
 async fn main() -> Result<(), Box<dyn StdError>> {
     let args = Args::parse();
-    init_tracing(&args.log_level);
+    if let Some(dir) = &args.work_dir {
+        std::env::set_current_dir(dir)?;
+    }
+    let log_format = args.log_format.parse().unwrap_or(logging::LogFormat::Text);
+    logging::init_tracing(&args.log_level, log_format);
 
     info!(
         "MaowBot starting. mode={}, headless={}, tui={}, auth={}",
@@ -84,8 +106,20 @@ async fn main() -> Result<(), Box<dyn StdError>> {
                 error!("Client error: {:?}", e);
             }
         }
+        "migrate-db" => {
+            if let Err(e) = crate::migrate_db::run_migrate_db(args).await {
+                error!("Database migration error: {:?}", e);
+            }
+        }
+        "service" => {
+            match tokio::task::spawn_blocking(move || crate::service::run(args)).await {
+                Ok(Err(e)) => error!("Service error: {:?}", e),
+                Err(e) => error!("Service thread panicked: {:?}", e),
+                Ok(Ok(())) => {}
+            }
+        }
         other => {
-            error!("Invalid mode '{}'. Use --mode=server or --mode=client.", other);
+            error!("Invalid mode '{}'. Use --mode=server, --mode=client or --mode=migrate-db.", other);
         }
     }
 
@@ -95,7 +129,16 @@ async fn main() -> Result<(), Box<dyn StdError>> {
 
 // Bring in the rest of our modules
 mod context;
+mod control_api;
+mod db_maintenance;
+mod diagnostics;
 mod server;
 mod client;
+mod migrate_db;
 pub mod portable_postgres;
-mod grpc_services;
\ No newline at end of file
+mod grpc_services;
+mod authz;
+mod logging;
+mod service;
+mod startup;
+mod tls;
\ No newline at end of file
@@ -0,0 +1,65 @@
+//! maowbot-server/src/migrate_db.rs
+//!
+//! `--mode migrate-db`: copy data from `--db <URL>` into `--migrate-to <URL>`,
+//! e.g. from the portable Postgres into an embedded SQLite file or back.
+//! Some data isn't copied (`transfer::NOT_COPIED`), so it only runs with
+//! `--partial`. Re-running it updates what an earlier run copied.
+
+use tracing::info;
+
+use maowbot_core::crypto::Encryptor;
+use maowbot_core::crypto::secrets::SecretsManager;
+use maowbot_core::db::transfer::{copy_all, BackendRepos, NOT_COPIED};
+use maowbot_core::db::DbBackend;
+use maowbot_core::Error;
+
+use crate::portable_postgres::*;
+use crate::Args;
+
+pub async fn run_migrate_db(args: Args) -> Result<(), Error> {
+    let Some(target_url) = args.migrate_to.as_deref() else {
+        return Err(Error::ValidationError(
+            "--mode migrate-db needs --migrate-to <postgres://... | sqlite://...>".into()
+        ));
+    };
+    let source_url = args.db_path.as_str();
+    if !NOT_COPIED.is_empty() && !args.partial {
+        return Err(Error::ValidationError(format!(
+            "migrate-db does not copy {}; pass --partial to migrate everything else",
+            NOT_COPIED.join(", ")
+        )));
+    }
+
+    let source_backend = DbBackend::from_url(source_url)?;
+    let target_backend = DbBackend::from_url(target_url)?;
+
+    // Same local Postgres the server would use
+    let pg_bin_dir = "./postgres/bin";
+    let pg_data_dir = "./postgres/data";
+    let uses_postgres = source_backend == DbBackend::Postgres || target_backend == DbBackend::Postgres;
+    if uses_postgres {
+        ensure_db_initialized(pg_bin_dir, pg_data_dir)?;
+        start_postgres(pg_bin_dir, pg_data_dir, 5432)?;
+        create_database(pg_bin_dir, 5432, "maowbot")?;
+    }
+
+    // Credentials are stored encrypted; both sides use the local secrets key
+    let secrets = SecretsManager::load_or_create()?;
+    let encryptor = Encryptor::new(secrets.key())?;
+
+    let result = async {
+        info!("Copying data from {} to {}", source_url, target_url);
+        let from = BackendRepos::connect(source_url, encryptor.clone()).await?;
+        let to = BackendRepos::connect(target_url, encryptor).await?;
+        copy_all(&from, &to).await
+    }.await;
+
+    if uses_postgres {
+        let _ = stop_postgres(pg_bin_dir, pg_data_dir);
+    }
+
+    let report = result?;
+    println!("Database migration finished. Copied:\n{}", report);
+    println!("Not copied: {}.", NOT_COPIED.join(", "));
+    Ok(())
+}
@@ -2,7 +2,7 @@
 use std::{
     fs::File,
     io::Read,
-    path::Path,
+    path::{Path, PathBuf},
     process::Command,
 };
 use std::io::Result as IoResult;
@@ -10,7 +10,13 @@ use std::process::Stdio;
 use std::time::Duration;
 use std::thread;
 use tokio::time::Instant;
-use tracing::{info, error, warn};
+use tracing::{info, warn};
+
+/// Bundled Postgres binaries and cluster, relative to the server's working directory.
+pub const PG_BIN_DIR: &str = "./postgres/bin";
+pub const PG_DATA_DIR: &str = "./postgres/data";
+/// Where `pg_dump` snapshots are kept.
+pub const SNAPSHOT_DIR: &str = "./postgres/snapshots";
 
 // ---------------------------------------------------------------------
 // Windows (actual embedded Postgres logic)
@@ -185,7 +191,7 @@ fn attempt_pg_ctl_fast_stop(pg_bin_dir: &str, data_dir: &str) -> IoResult<bool>
     let pg_ctl_path = format!("{}/pg_ctl", pg_bin_dir);
 
     let mut child = Command::new(&pg_ctl_path)
-        .args(&["stop", "-D", data_dir, "-m", "fast"])
+        .args(["stop", "-D", data_dir, "-m", "fast"])
         .stdout(Stdio::null())
         .stderr(Stdio::null())
         .spawn()?;
@@ -274,3 +280,142 @@ pub fn kill_leftover_postgres_if_any(pg_bin_dir: &str, data_dir: &str) -> IoResu
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------
+// Snapshots, WAL/log cleanup and data-dir checks. These work against the
+// bundled binaries when present and fall back to the ones on PATH, so they
+// also cover a system Postgres on Linux.
+// ---------------------------------------------------------------------
+
+/// The bundled tool if it exists, otherwise the bare name (resolved via PATH).
+fn pg_tool(pg_bin_dir: &str, name: &str) -> PathBuf {
+    let exe = if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+    let bundled = Path::new(pg_bin_dir).join(&exe);
+    if bundled.exists() { bundled } else { PathBuf::from(exe) }
+}
+
+/// Writes a custom-format `pg_dump` of `db_url` to `dest`. A partial file is removed on failure.
+/// Restore with `pg_restore --clean --if-exists -d <db_url> <dest>`.
+pub fn dump_database(pg_bin_dir: &str, db_url: &str, dest: &Path) -> IoResult<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let output = Command::new(pg_tool(pg_bin_dir, "pg_dump"))
+        .arg("--format=custom")
+        .arg("--no-owner")
+        .arg("--file").arg(dest)
+        .arg("--dbname").arg(db_url)
+        .stdout(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(dest);
+        return Err(std::io::Error::other(format!(
+            "pg_dump failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    info!("Database snapshot written to {}", dest.display());
+    Ok(())
+}
+
+/// Total size of the files under `path` (0 if it doesn't exist).
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else { return 0 };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Size of the write-ahead log of the bundled cluster.
+pub fn wal_size(data_dir: &str) -> u64 {
+    dir_size(&Path::new(data_dir).join("pg_wal"))
+}
+
+/// Moves `server.log` to `server.log.1` (replacing the previous one) once it passes `max_bytes`.
+pub fn rotate_server_log(data_dir: &str, max_bytes: u64) -> IoResult<bool> {
+    let log = Path::new(data_dir).join("server.log");
+    match std::fs::metadata(&log) {
+        Ok(meta) if meta.len() > max_bytes => {
+            let rotated = Path::new(data_dir).join("server.log.1");
+            let _ = std::fs::remove_file(&rotated);
+            // Postgres keeps the file open; copy + truncate instead of rename
+            std::fs::copy(&log, &rotated)?;
+            File::create(&log)?;
+            info!("Rotated {} ({} bytes)", log.display(), meta.len());
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Problems found in the bundled cluster's data directory before it is started.
+#[derive(Debug, Default, Clone)]
+pub struct DataDirCheck {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Checks that the data dir is complete, matches the bundled server's major
+/// version, and whether it was shut down cleanly. Skipped when there is no
+/// bundled cluster.
+pub fn check_data_dir(pg_bin_dir: &str, data_dir: &str) -> DataDirCheck {
+    let mut check = DataDirCheck::default();
+    let dir = Path::new(data_dir);
+    if !dir.exists() {
+        return check;
+    }
+
+    let cluster_version = match std::fs::read_to_string(dir.join("PG_VERSION")) {
+        Ok(v) => Some(v.trim().to_string()),
+        Err(_) => {
+            check.errors.push(format!("{} exists but has no PG_VERSION; the cluster is incomplete", data_dir));
+            None
+        }
+    };
+    for sub in ["global", "base", "pg_wal"] {
+        if cluster_version.is_some() && !dir.join(sub).is_dir() {
+            check.errors.push(format!("{} is missing its {}/ directory", data_dir, sub));
+        }
+    }
+
+    let postgres = Path::new(pg_bin_dir).join(if cfg!(windows) { "postgres.exe" } else { "postgres" });
+    if let (Some(cluster), true) = (&cluster_version, postgres.exists()) {
+        if let Ok(out) = Command::new(&postgres).arg("--version").output() {
+            // "postgres (PostgreSQL) 16.2" => "16"
+            let text = String::from_utf8_lossy(&out.stdout);
+            let major = text.split_whitespace().last().and_then(|v| v.split('.').next()).unwrap_or("");
+            if !major.is_empty() && major != cluster {
+                check.errors.push(format!(
+                    "Data directory is from PostgreSQL {} but the bundled server is {}; restore a snapshot or pg_upgrade",
+                    cluster, major
+                ));
+            }
+        }
+    }
+
+    let controldata = Path::new(pg_bin_dir).join(if cfg!(windows) { "pg_controldata.exe" } else { "pg_controldata" });
+    if controldata.exists() {
+        if let Ok(out) = Command::new(&controldata).arg("-D").arg(data_dir).output() {
+            let text = String::from_utf8_lossy(&out.stdout);
+            let state = text.lines()
+                .find_map(|l| l.strip_prefix("Database cluster state:"))
+                .map(|s| s.trim().to_string());
+            match state.as_deref() {
+                Some("shut down") | None => {}
+                Some("in production") => check.warnings.push(
+                    "Postgres was not shut down cleanly; it will replay the WAL on start".to_string()
+                ),
+                Some(other) => check.warnings.push(format!("Cluster state is '{}'", other)),
+            }
+        }
+    }
+
+    check
+}
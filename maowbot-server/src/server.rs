@@ -2,22 +2,17 @@
 //!
 //! The main server logic: building the ServerContext and running the gRPC plugin service.
 
-use maowbot_core::tasks::credential_refresh::refresh_expiring_tokens;
-use maowbot_common::traits::repository_traits::BotConfigRepository;
+use maowbot_core::tasks::credential_refresh::{spawn_credential_refresh_task, CredentialRefreshSchedule};
 use std::sync::Arc;
 use std::net::SocketAddr;
 use std::time::Duration;
 use tokio::time;
 use tracing::{info, error, warn};
-use tonic::transport::{Server, Identity, ServerTlsConfig};
-use std::fs;
+use tonic::transport::Server;
 use std::path::Path;
-use std::io::Write;
-use rcgen::{generate_simple_self_signed};
 use maowbot_core::Error;
 use maowbot_core::eventbus::{BotEvent};
-use maowbot_core::eventbus::db_logger::{spawn_db_logger_task};
-use maowbot_core::eventbus::db_logger_handle::DbLoggerControl;
+use maowbot_core::eventbus::db_logger::{spawn_db_logger_task_with_config, DbLoggerConfig, DEFAULT_MAX_BUFFERED};
 use maowbot_core::plugins::service_grpc::PluginServiceGrpc;
 use maowbot_proto::plugs::plugin_service_server::PluginServiceServer;
 use maowbot_core::plugins::manager::PluginManager;
@@ -42,23 +37,41 @@ use maowbot_proto::maowbot::services::{
     autostart_service_server::AutostartServiceServer,
     obs_service_server::ObsServiceServer,
     event_pipeline::event_pipeline_service_server::EventPipelineServiceServer,
+    chat_archive_service_server::ChatArchiveServiceServer,
+    giveaway_service_server::GiveawayServiceServer,
+    crowd_vote_service_server::CrowdVoteServiceServer,
+    protection_service_server::ProtectionServiceServer,
+    moderation_rules_service_server::ModerationRulesServiceServer,
+    emote_stats_service_server::EmoteStatsServiceServer,
+    emote_set_service_server::EmoteSetServiceServer,
+    localization_service_server::LocalizationServiceServer,
+    event_stream_service_server::EventStreamServiceServer,
+    ui_settings_service_server::UiSettingsServiceServer,
+    responder_service_server::ResponderServiceServer,
+    alerting_service_server::AlertingServiceServer,
+    health_service_server::HealthServiceServer,
+    stream_session_service_server::StreamSessionServiceServer,
+    export_service_server::ExportServiceServer,
 };
 
 use crate::Args;
+use crate::authz::{AuthzLayer, TokenStore};
 use crate::context::ServerContext;
-use crate::portable_postgres::*;
+use crate::startup::{ReadinessGateLayer, Startup, Subsystem};
 use maowbot_core::tasks::biweekly_maintenance::{
     spawn_biweekly_maintenance_task, run_partition_maintenance
 };
 use maowbot_core::tasks::credential_refresh::refresh_all_refreshable_credentials;
 use maowbot_core::tasks::autostart::run_autostart;
 use maowbot_core::tasks::redeem_sync;
-use maowbot_core::tasks::discord_live_role;
+use maowbot_core::platforms::supervisor::ConnectionState;
+use maowbot_core::updater::{RestartState, RESTART_STATE_PATH};
 use maowbot_tui::TuiModule;
 
 pub async fn run_server(args: Args) -> Result<(), Error> {
-    // Build the global context
-    let mut ctx = ServerContext::new(&args).await?;
+    // Build the global context; the database and repositories come up in here
+    let mut ctx = ServerContext::new(&args, Arc::new(Startup::new())).await?;
+    let startup = ctx.startup.clone();
 
     // Start OSC server in background to avoid blocking server startup
     let osc_manager_clone = ctx.osc_manager.clone();
@@ -73,169 +86,78 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
 
     // 1) Spawn DB logger
     // Get configuration for db logger
-    let buffer_size = ctx.bot_config_repo.get_value("chat_logging.batch_size").await
-        .ok()
-        .flatten()
-        .and_then(|v| v.parse::<usize>().ok())
-        .unwrap_or(100);
-    
-    let flush_interval = ctx.bot_config_repo.get_value("chat_logging.flush_interval_seconds").await
-        .ok()
-        .flatten()
-        .and_then(|v| v.parse::<u64>().ok())
-        .unwrap_or(5);
+    let buffer_size = ctx.settings.get_u64("chat_logging.batch_size").unwrap_or(100) as usize;
+    let flush_interval = ctx.settings.get_u64("chat_logging.flush_interval_seconds").unwrap_or(5);
     
+    let logger_config = DbLoggerConfig {
+        max_buffered: ctx.settings.get_u64("chat_logging.max_buffered").unwrap_or(DEFAULT_MAX_BUFFERED as u64) as usize,
+        shed_percent: ctx.settings.get_u64("chat_logging.shed_percent").unwrap_or(80).min(100) as u8,
+        ..DbLoggerConfig::new(buffer_size, flush_interval)
+    };
+
     info!("Starting DB logger with buffer_size={}, flush_interval={}s", buffer_size, flush_interval);
     
-    // Create a concrete analytics repo for the db logger
-    let analytics_repo_for_logger = maowbot_core::repositories::postgres::analytics::PostgresAnalyticsRepository::new(
-        ctx.db.pool().clone()
-    );
-    
-    let (db_logger_handle, db_logger_control) = spawn_db_logger_task(
+    let (db_logger_handle, db_logger_control) = spawn_db_logger_task_with_config(
         &ctx.event_bus,
-        analytics_repo_for_logger,
-        buffer_size,
-        flush_interval,
+        ctx.repos.analytics.clone(),
+        logger_config,
     );
     ctx.db_logger_control = Some(db_logger_control);
     
     let ctx = Arc::new(ctx);
+    // Lets a Windows service stop request run the normal shutdown below
+    crate::service::attach_event_bus(ctx.event_bus.clone());
+
     // 2) Spawn maintenance
     let _maintenance_task = spawn_biweekly_maintenance_task(
         ctx.db.clone(),
-        maowbot_core::repositories::postgres::user_analysis::PostgresUserAnalysisRepository::new(ctx.db.pool().clone()),
-        ctx.event_bus.clone()
+        ctx.repos.user_analysis.clone(),
+        ctx.repos.chat_archive.clone(),
+        ctx.event_bus.clone(),
+        ctx.retention.clone(),
     );
 
-    // Run partition maintenance immediately on startup to ensure partitions exist
-    info!("Running partition maintenance on startup...");
-    if let Err(e) = run_partition_maintenance(&ctx.db).await {
-        error!("Failed to run partition maintenance on startup: {:?}", e);
-        // Continue startup even if partition maintenance fails
+    // Run partition maintenance immediately on startup to ensure partitions exist (Postgres only)
+    if let Some(db) = ctx.db.postgres() {
+        info!("Running partition maintenance on startup...");
+        if let Err(e) = run_partition_maintenance(db).await {
+            error!("Failed to run partition maintenance on startup: {:?}", e);
+            // Continue startup even if partition maintenance fails
+        }
     }
 
-    redeem_sync::sync_channel_redeems(
-        &ctx.redeem_service,
-        &ctx.platform_manager,
-        &ctx.message_service.user_service,
-        &*ctx.bot_config_repo.clone(),
-        false
-    ).await?;
+    // 3) Auth: API tokens for the gRPC services, then platform credentials
+    startup.begin(Subsystem::Auth).map_err(Error::Internal)?;
+    let mut auth_problems = Vec::new();
+
+    // API tokens for the authz layer; local clients get an admin token file on first start
+    let api_tokens = TokenStore::load(ctx.repos.api_tokens.clone()).await?;
+    if let Err(e) = api_tokens.ensure_local_admin_token().await {
+        warn!("Local admin API token unavailable: {}", e);
+    }
 
-    // 3) Refresh credentials
     {
         let mut auth_lock = ctx.auth_manager.lock().await;
-        if let Err(e) = refresh_all_refreshable_credentials(ctx.creds_repo.as_ref(), &mut *auth_lock).await {
+        if let Err(e) = refresh_all_refreshable_credentials(ctx.repos.credentials.as_ref(), &mut auth_lock).await {
             error!("Failed to refresh credentials on startup => {:?}", e);
+            auth_problems.push(format!("credential refresh failed: {}", e));
         }
     }
 
-    let creds_repo_clone = ctx.creds_repo.clone();
-    let auth_manager_clone = ctx.auth_manager.clone();
-    let _refresh_task = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(30 * 60)); // Every 30 minutes
-        loop {
-            interval.tick().await;
-
-            let mut auth_lock = auth_manager_clone.lock().await;
-            match refresh_expiring_tokens(
-                creds_repo_clone.as_ref(),
-                &mut *auth_lock,
-                60 // Refresh tokens expiring within 60 minutes
-            ).await {
-                Ok(_) => info!("Periodic token refresh completed"),
-                Err(e) => error!("Periodic token refresh failed: {:?}", e),
-            }
-        }
-    });
+    // Twitch tokens granted before the bot asked for a scope; degraded features get a re-auth prompt
+    ctx.scope_check.start();
 
-    // Create a proper BotApiWrapper that implements all BotApi traits including AiApi
-    let bot_api = Arc::new(BotApiWrapper::new(ctx.plugin_manager.clone()));
-    
-    // 4) Autostart any configured accounts
-    if let Err(e) = run_autostart(ctx.autostart_repo.as_ref(), bot_api.clone()).await {
-        error!("Autostart error => {:?}", e);
-    }
-    
-    // 4.5) Spawn Discord live role verification task after autostart
-    // This task will check all users for streaming status and update roles at startup
-    let _discord_live_role_startup_task = maowbot_core::tasks::discord_live_role::spawn_discord_live_role_startup_task(
-        ctx.platform_manager.clone(),
-        ctx.plugin_manager.discord_repo.clone()
+    // Renew OAuth tokens before they expire; failures go out as CredentialRefreshFailed events
+    let _refresh_task = spawn_credential_refresh_task(
+        ctx.repos.credentials.clone(),
+        ctx.auth_manager.clone(),
+        ctx.event_bus.clone(),
+        CredentialRefreshSchedule::default(),
     );
-    
-    // 4.6) Spawn periodic Discord live role check task
-    // This task will regularly check and update streaming status
-    let _discord_live_role_periodic_task = {
-        // Find first active Discord account for periodic checks
-        let discord_platform = {
-            // Check active runtimes for Discord platforms
-            let runtimes = ctx.platform_manager.active_runtimes.try_lock();
-            if let Ok(guard) = runtimes {
-                // Find first Discord instance directly from the runtime handle
-                let discord_instance = guard.iter()
-                    .find(|((platform, _), _)| platform == "discord")
-                    .and_then(|((_platform, _account), handle)| handle.discord_instance.clone());
-                
-                if let Some(discord) = discord_instance {
-                    Some(discord)
-                } else {
-                    error!("No Discord instances available for live role periodic task");
-                    None
-                }
-            } else {
-                error!("Failed to lock active runtimes for live role periodic task");
-                None
-            }
-        };
-        
-        if let Some(discord) = discord_platform {
-            // If we found an active Discord platform, spawn the periodic task
-            maowbot_core::tasks::discord_live_role::spawn_discord_live_role_task(
-                discord,
-                ctx.plugin_manager.discord_repo.clone()
-            )
-        } else {
-            // Otherwise, create a dummy task that just logs and exits
-            tokio::spawn(async move {
-                warn!("Discord live role periodic task not started - no Discord instances available");
-            })
-        }
-    };
-    
-    // 5) If TUI was requested (DEPRECATED)
-    if args.tui {
-        warn!("⚠️  The --tui flag is deprecated! Use the standalone 'maowbot-tui' binary instead.");
-        warn!("⚠️  The built-in TUI will be removed in a future version.");
-        warn!("⚠️  Run 'maowbot-tui --with-server' to start both TUI and server together.");
-        
-        let tui_module = Arc::new(TuiModule::new(bot_api.clone(), ctx.event_bus.clone()).await);
-        tui_module.spawn_tui_thread().await;
-    }
-    
-    // Let active plugins see the BotApi
-    {
-        let lock = ctx.plugin_manager.plugins.lock().await;
-        for p in lock.iter() {
-            p.set_bot_api(bot_api.clone());
-        }
-    }
-
-    let eventsub_svc_clone = ctx.eventsub_service.clone();
-    tokio::spawn(async move {
-        eventsub_svc_clone.start().await;
-    });
+    finish_stage(&startup, Subsystem::Auth, auth_problems, String::new());
 
-    // Start the event pipeline service
-    let event_pipeline_svc_clone = ctx.event_pipeline_service.clone();
-    tokio::spawn(async move {
-        event_pipeline_svc_clone.start().await;
-    });
-
-    // 6) Start the gRPC server
-    let identity = load_or_generate_certs()?;
-    let tls_config = ServerTlsConfig::new().identity(identity);
+    // 4) Start the gRPC listener; until startup finishes only the health service answers
+    let tls_config = crate::tls::server_tls_config(&ctx.settings)?;
     let addr: SocketAddr = args.server_addr.parse()?;
     info!("Starting Tonic gRPC server on {}", addr);
 
@@ -248,30 +170,39 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
         ctx.plugin_manager.user_repo.clone(),
         ctx.plugin_manager.user_analysis_repo.clone(),
         ctx.plugin_manager.platform_identity_repo.clone(),
+        ctx.repos.user_notes.clone(),
+        ctx.viewer_card_service.clone(),
+        ctx.analysis_recompute.clone(),
     );
     
+    // Selects the workspace for scoped requests (x-maowbot-workspace metadata)
+    let workspaces = WorkspaceResolver::new(ctx.repos.workspaces.clone());
+
     let credential_service = CredentialServiceImpl::new(
         ctx.auth_manager.clone(),
-        ctx.creds_repo.clone(),
-        ctx.plugin_manager.user_repo.clone(),
+        ctx.repos.clone(),
+        workspaces.clone(),
+        ctx.scope_check.clone(),
+        ctx.chat_identities.clone(),
     );
     
-    let platform_config_repo = Arc::new(maowbot_core::repositories::postgres::platform_config::PostgresPlatformConfigRepository::new(
-        ctx.db.pool().clone()
-    ));
-    
     let platform_service = PlatformServiceImpl::new(
         ctx.platform_manager.clone(),
-        platform_config_repo,
+        ctx.repos.platform_configs.clone(),
     );
     
     let new_plugin_service = PluginServiceImpl::new(
         ctx.plugin_manager.clone(),
+        ctx.clone(),
     );
     
     // Build the server with all services
     let server_future = Server::builder()
         .tls_config(tls_config)?
+        // Everything but the health service answers Unavailable until startup finishes
+        .layer(ReadinessGateLayer::new(startup.clone()))
+        // Every request needs an API token whose role grants the method's permission
+        .layer(AuthzLayer::new(api_tokens.clone()))
         // Legacy plugin service
         .add_service(PluginServiceServer::new(plugin_service_impl))
         // New services
@@ -280,8 +211,17 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
         .add_service(PlatformServiceServer::new(platform_service))
         .add_service(NewPluginServiceServer::new(new_plugin_service))
         .add_service(ConfigServiceServer::new(ConfigServiceImpl::new(
-            ctx.bot_config_repo.clone(),
+            ctx.repos.clone(),
+            ctx.settings.clone(),
+            ctx.updater.clone(),
+            ctx.db_maintenance.clone(),
+            ctx.retention.clone(),
             ctx.event_bus.clone(),
+            ctx.secrets.clone(),
+            ctx.encryptor.clone(),
+            workspaces.clone(),
+            ctx.workspace_router.clone(),
+            api_tokens.clone(),
         )))
         .add_service(AiServiceServer::new({
             // Get the AI API implementation from the plugin manager
@@ -292,42 +232,94 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
             }
         }))
         .add_service(CommandServiceServer::new(CommandServiceImpl::new(
-            ctx.command_repo.clone(),
-            ctx.command_usage_repo.clone(),
+            ctx.repos.clone(),
+            workspaces.clone(),
         )))
         .add_service(RedeemServiceServer::new(RedeemServiceImpl::new(
-            ctx.redeem_repo.clone(),
-            ctx.redeem_usage_repo.clone(),
+            ctx.repos.clone(),
             ctx.redeem_service.clone(),
+            ctx.redeem_schedule_service.clone(),
+            ctx.redeem_approval_service.clone(),
+            workspaces.clone(),
         )))
         .add_service(TwitchServiceServer::new(TwitchServiceImpl::new(
             ctx.platform_manager.clone(),
+            ctx.stream_marker_service.clone(),
+            ctx.schedule_service.clone(),
         )))
         .add_service(DiscordServiceServer::new(DiscordServiceImpl::new(
             ctx.plugin_manager.clone(),
-            Arc::new(maowbot_core::repositories::postgres::discord::PostgresDiscordRepository::new(
-                ctx.db.pool().clone()
-            )),
+            ctx.repos.discord.clone(),
         )))
         .add_service(VrChatServiceServer::new(VRChatServiceImpl::new(
             ctx.plugin_manager.clone(),
+            ctx.vrchat_session_service.clone(),
+            ctx.vrchat_group_service.clone(),
         )))
         .add_service(OscServiceServer::new(OscServiceImpl::new(
             ctx.plugin_manager.clone(),
-            Arc::new(maowbot_core::repositories::postgres::osc_toggle::PostgresOscToggleRepository::new(
-                ctx.db.pool().clone()
-            )),
+            ctx.repos.osc_toggles.clone(),
+            ctx.midi_service.clone(),
         )))
         .add_service(AutostartServiceServer::new(AutostartServiceImpl::new(
-            ctx.autostart_repo.clone(),
+            ctx.repos.autostart.clone(),
         )))
         .add_service(ObsServiceServer::new(ObsServiceImpl::new(
             ctx.platform_manager.clone(),
-            ctx.obs_repo.clone(),
+            ctx.repos.obs.clone(),
         )))
         .add_service(EventPipelineServiceServer::new(EventPipelineServiceImpl::new(
             ctx.clone(),
         )))
+        .add_service(ChatArchiveServiceServer::new(ChatArchiveServiceImpl::new(
+            ctx.repos.chat_archive.clone(),
+            ctx.plugin_manager.user_repo.clone(),
+            ctx.settings.clone(),
+        )))
+        .add_service(GiveawayServiceServer::new(GiveawayServiceImpl::new(
+            ctx.giveaway_service.clone(),
+        )))
+        .add_service(CrowdVoteServiceServer::new(CrowdVoteServiceImpl::new(
+            ctx.crowd_vote_service.clone(),
+        )))
+        .add_service(ProtectionServiceServer::new(ProtectionServiceImpl::new(
+            ctx.protection_service.clone(),
+        )))
+        .add_service(ModerationRulesServiceServer::new(ModerationRulesServiceImpl::new(
+            ctx.moderation_service.clone(),
+        )))
+        .add_service(EmoteStatsServiceServer::new(EmoteStatsServiceImpl::new(
+            ctx.emote_stats_service.clone(),
+        )))
+        .add_service(EmoteSetServiceServer::new(EmoteSetServiceImpl::new(
+            ctx.emote_set_service.clone(),
+        )))
+        .add_service(LocalizationServiceServer::new(LocalizationServiceImpl::new(
+            ctx.localizer.clone(),
+        )))
+        .add_service(EventStreamServiceServer::new(EventStreamServiceImpl::new(
+            ctx.ui_events.clone(),
+        )))
+        .add_service(UiSettingsServiceServer::new(UiSettingsServiceImpl::new(
+            ctx.ui_settings.clone(),
+        )))
+        .add_service(ResponderServiceServer::new(ResponderServiceImpl::new(
+            ctx.responder_service.clone(),
+        )))
+        .add_service(AlertingServiceServer::new(AlertingServiceImpl::new(
+            ctx.alerting_service.clone(),
+        )))
+        .add_service(HealthServiceServer::new(HealthServiceImpl::new(
+            startup.clone(),
+        )))
+        .add_service(StreamSessionServiceServer::new(StreamSessionServiceImpl::new(
+            ctx.stream_session_service.clone(),
+        )))
+        .add_service(ExportServiceServer::new(ExportServiceImpl::new(
+            Arc::new(maowbot_core::services::data_export::DataExportService::new(
+                ctx.repos.exports.clone(),
+            )),
+        )))
         .serve(addr);
 
     let event_bus = ctx.event_bus.clone();
@@ -337,6 +329,223 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
         }
     });
 
+    // 5) Platforms: runtimes, the services built on them, EventSub and pipelines
+    startup.begin(Subsystem::Platforms).map_err(Error::Internal)?;
+    let mut platform_problems = Vec::new();
+
+    redeem_sync::sync_channel_redeems(
+        &ctx.redeem_service,
+        &ctx.platform_manager,
+        &ctx.message_service.user_service,
+        ctx.repos.bot_config.as_ref(),
+        false
+    ).await?;
+
+    // Restart dropped platform connections with backoff; transitions go out as PlatformConnectionChanged events
+    let _connection_supervisor = ctx.platform_manager.spawn_connection_supervisor(Duration::from_secs(1));
+
+    // Pick up bot_config changes made outside the registry; each one is published as ConfigChanged
+    let _settings_reload = ctx.settings.spawn_reload_task(Duration::from_secs(crate::context::SETTINGS_RELOAD_INTERVAL_SECS));
+
+    // Create a proper BotApiWrapper that implements all BotApi traits including AiApi
+    let bot_api = Arc::new(BotApiWrapper::new(ctx.plugin_manager.clone()));
+    
+    // 5.1) Autostart any configured accounts
+    if let Err(e) = run_autostart(ctx.repos.autostart.as_ref(), bot_api.clone()).await {
+        error!("Autostart error => {:?}", e);
+        platform_problems.push(format!("autostart failed: {}", e));
+    }
+
+    // 5.2) Coming back from an update restart => bring back what was running before it
+    if let Some(state) = RestartState::take(Path::new(RESTART_STATE_PATH)) {
+        info!("Restarted after updating {} -> {}; restoring {} runtime(s)",
+            state.from_version, state.to_version, state.runtimes.len());
+        restore_runtimes(&ctx, &state).await;
+    }
+
+    // 5.3) Database integrity check, then scheduled snapshots/compaction
+    ctx.db_maintenance.check_integrity_on_startup().await;
+    let _db_maintenance_schedule = ctx.db_maintenance.spawn_schedule();
+
+    // 5.4) Scheduled release checks (updater.check_interval_hours / updater.auto_update)
+    let _update_schedule = ctx.updater.spawn_schedule();
+
+    // 5.5) Streamlabs/StreamElements sockets (donations.* tokens)
+    ctx.donation_service.start();
+
+    // Pulsoid/HypeRate heart rate (heart_rate.source)
+    ctx.heart_rate_service.start();
+
+    // MIDI controller mappings (midi.device)
+    ctx.midi_service.start();
+
+    // Stream markers on raids/redeems/scene changes, chapters at stream end
+    ctx.stream_marker_service.start();
+
+    // Giveaways left open before a restart resume taking entries
+    ctx.giveaway_service.start();
+
+    // Crowd control votes count chat votes and decide when their window ends
+    ctx.crowd_vote_service.start();
+
+    // Raid defense watches the broadcaster's chat for spikes
+    ctx.protection_service.start();
+
+    // Bot account scoring for new chatters, also fed to raid defense
+    ctx.bot_detection_service.start();
+
+    // Link/phrase blocklist rules
+    ctx.moderation_service.start();
+
+    // Emote usage counts for !emotestats and the GUI
+    ctx.emote_stats_service.start();
+
+    // 7TV/BTTV/FFZ emote sets for stats, completion and chat rendering
+    ctx.emote_set_service.start();
+
+    // Chat, alerts, connections and OSC for SubscribeEvents, journaled for resuming
+    ctx.ui_events.start();
+
+    // Shared GUI/overlay settings for StreamUiSettings
+    if let Err(e) = ctx.ui_settings.load().await {
+        error!("Failed to load UI settings: {:?}", e);
+    }
+
+    // New Twitch clips posted to Discord (clips.discord_*)
+    ctx.clip_service.start();
+
+    // Redeem schedules (time windows, category, hype train)
+    ctx.redeem_schedule_service.start();
+
+    // Refunds redemptions nobody approved in time
+    ctx.redeem_approval_service.start();
+
+    // VRChat session keep-alive and expiry alerts
+    ctx.vrchat_session_service.start();
+
+    // Friends joining/leaving the streamer's VRChat instance
+    ctx.vrchat_presence_service.start();
+
+    // Viewers joining/leaving Twitch chat, lurkers and watchtime
+    ctx.chatter_presence_service.start();
+
+    // Starting-soon scene and announcement before scheduled Twitch streams
+    ctx.schedule_service.start();
+
+    // Hype moments: markers, replay buffer saves and events for recaps
+    ctx.hype_detector.start();
+
+    // Sub anniversaries/streaks and follow anniversaries in chat, overlays and OSC
+    ctx.milestone_service.start();
+
+    // One stream session per broadcast, with per-stream stats
+    ctx.stream_session_service.start();
+
+    // Regex/keyword responders answering chat
+    ctx.responder_service.start();
+
+    // Ops alerts to Discord DMs, chat or the GUI, per the alert rules
+    ctx.alerting_service.start();
+
+    // Filtered Twitch chat in the VRChat chatbox
+    ctx.osc_chat_relay.start();
+
+    // Chat summaries for the AI's channel memory
+    ctx.chat_summarizer.start();
+
+    // VRChat group join requests and going-live posts
+    ctx.vrchat_group_service.start();
+    
+    // 5.6) Spawn Discord live role verification task after autostart
+    // This task will check all users for streaming status and update roles at startup
+    let _discord_live_role_startup_task = maowbot_core::tasks::discord_live_role::spawn_discord_live_role_startup_task(
+        ctx.platform_manager.clone(),
+        ctx.plugin_manager.discord_repo.clone()
+    );
+    
+    // 5.7) Spawn periodic Discord live role check task
+    // This task will regularly check and update streaming status
+    let _discord_live_role_periodic_task = {
+        // Find first active Discord account for periodic checks
+        let discord_platform = {
+            // Check active runtimes for Discord platforms
+            let runtimes = ctx.platform_manager.active_runtimes.try_lock();
+            if let Ok(guard) = runtimes {
+                // Find first Discord instance directly from the runtime handle
+                let discord_instance = guard.iter()
+                    .find(|((platform, _), _)| platform == "discord")
+                    .and_then(|((_platform, _account), handle)| handle.discord_instance.clone());
+                
+                if let Some(discord) = discord_instance {
+                    Some(discord)
+                } else {
+                    error!("No Discord instances available for live role periodic task");
+                    None
+                }
+            } else {
+                error!("Failed to lock active runtimes for live role periodic task");
+                None
+            }
+        };
+        
+        if let Some(discord) = discord_platform {
+            // If we found an active Discord platform, spawn the periodic task
+            maowbot_core::tasks::discord_live_role::spawn_discord_live_role_task(
+                discord,
+                ctx.plugin_manager.discord_repo.clone()
+            )
+        } else {
+            // Otherwise, create a dummy task that just logs and exits
+            tokio::spawn(async move {
+                warn!("Discord live role periodic task not started - no Discord instances available");
+            })
+        }
+    };
+
+    let eventsub_svc_clone = ctx.eventsub_service.clone();
+    tokio::spawn(async move {
+        eventsub_svc_clone.start().await;
+    });
+
+    // Start the event pipeline service
+    let event_pipeline_svc_clone = ctx.event_pipeline_service.clone();
+    tokio::spawn(async move {
+        event_pipeline_svc_clone.start().await;
+    });
+
+    let runtimes = ctx.platform_manager.active_runtimes.lock().await.len();
+    finish_stage(&startup, Subsystem::Platforms, platform_problems, format!("{} runtime(s) running", runtimes));
+
+    // 6) Plugins
+    startup.begin(Subsystem::Plugins).map_err(Error::Internal)?;
+
+    // If TUI was requested (DEPRECATED)
+    if args.tui {
+        warn!("⚠️  The --tui flag is deprecated! Use the standalone 'maowbot-tui' binary instead.");
+        warn!("⚠️  The built-in TUI will be removed in a future version.");
+        warn!("⚠️  Run 'maowbot-tui --with-server' to start both TUI and server together.");
+        
+        let tui_module = Arc::new(TuiModule::new(bot_api.clone(), ctx.event_bus.clone()).await);
+        tui_module.spawn_tui_thread().await;
+    }
+    
+    // Let active plugins see the BotApi
+    let plugin_count = {
+        let lock = ctx.plugin_manager.plugins.lock().await;
+        for p in lock.iter() {
+            p.set_bot_api(bot_api.clone());
+        }
+        lock.len()
+    };
+    startup.ready(Subsystem::Plugins, format!("{} plugin(s)", plugin_count));
+
+    // 7) Open the gRPC services and the HTTP control API
+    startup.begin(Subsystem::Grpc).map_err(Error::Internal)?;
+
+    // HTTP control surface for Stream Deck and similar tools (control_api.*), on the same tokens
+    let _control_api = crate::control_api::spawn(ctx.clone(), api_tokens.clone());
+    startup.ready(Subsystem::Grpc, format!("listening on {}", addr));
+
     // Ctrl-C => signal
     let eb_for_ctrlc = event_bus.clone();
     let _ctrlc_handle = tokio::spawn(async move {
@@ -347,7 +556,7 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
         eb_for_ctrlc.shutdown();
     });
 
-    // 7) Main loop => send Tick events until we see shutdown
+    // 8) Main loop => send Tick events until we see shutdown
     let mut shutdown_rx = event_bus.shutdown_rx.clone();
     loop {
         tokio::select! {
@@ -376,6 +585,12 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
         info!("OSC receiver channel closed");
     }
 
+    // An installed update asked for this shutdown => remember what was running
+    let relaunch = ctx.updater.restart_requested();
+    if relaunch {
+        save_restart_state(&ctx);
+    }
+
     // Cleanup
     info!("Stopping gRPC server...");
     srv_handle.abort();
@@ -385,35 +600,62 @@ pub async fn run_server(args: Args) -> Result<(), Error> {
 
     // Ensure DB logger is done
     db_logger_handle.abort();
+
+    if relaunch {
+        info!("Relaunching into the updated binary...");
+        if let Err(e) = ctx.updater.relaunch() {
+            error!("Failed to relaunch after update: {:?}", e);
+        }
+    }
     
+    crate::service::report_stopped();
+
     // Force exit to ensure the process terminates
     // This is necessary when the server is started from console
     std::process::exit(0)
 }
 
-
-/// Load or generate self-signed TLS cert for gRPC.
-fn load_or_generate_certs() -> Result<Identity, Error> {
-    let cert_folder = "certs";
-    let cert_path = format!("{}/server.crt", cert_folder);
-    let key_path  = format!("{}/server.key", cert_folder);
-
-    if Path::new(&cert_path).exists() && Path::new(&key_path).exists() {
-        let cert_pem = fs::read(&cert_path)?;
-        let key_pem  = fs::read(&key_path)?;
-        return Ok(Identity::from_pem(cert_pem, key_pem));
+/// Marks `subsystem` ready, or degraded with what went wrong while starting it.
+fn finish_stage(startup: &Startup, subsystem: Subsystem, problems: Vec<String>, summary: String) {
+    if problems.is_empty() {
+        startup.ready(subsystem, summary);
+    } else {
+        startup.degraded(subsystem, problems.join("; "));
     }
+}
 
-    let alt_names = vec!["localhost".to_string(), "127.0.0.1".to_string(), "0.0.0.0".to_string()];
-    let certified = generate_simple_self_signed(alt_names)?;
-    let cert_pem = certified.cert.pem();
-    let key_pem = certified.key_pair.serialize_pem();
-
-    fs::create_dir_all(cert_folder)?;
-    fs::File::create(&cert_path)?.write_all(cert_pem.as_bytes())?;
-    fs::File::create(&key_path)?.write_all(key_pem.as_bytes())?;
+/// Writes the runtimes the supervisor considers up so the updated process can restart them.
+fn save_restart_state(ctx: &ServerContext) {
+    let status = ctx.updater.status();
+    let state = RestartState {
+        from_version: status.current_version,
+        to_version: status.latest.map(|r| r.version).unwrap_or_default(),
+        runtimes: ctx.platform_manager.connection_supervisor.snapshots()
+            .into_iter()
+            .filter(|s| !matches!(s.state, ConnectionState::Failed | ConnectionState::Stopped))
+            .map(|s| (s.platform, s.account_name))
+            .collect(),
+    };
+    if let Err(e) = state.save(Path::new(RESTART_STATE_PATH)) {
+        error!("Failed to save restart state: {:?}", e);
+    }
+}
 
-    Ok(Identity::from_pem(cert_pem, key_pem))
+/// Starts the runtimes from a restart state that autostart didn't already bring up.
+async fn restore_runtimes(ctx: &ServerContext, state: &RestartState) {
+    let running: Vec<(String, String)> = ctx.platform_manager.connection_supervisor.snapshots()
+        .into_iter()
+        .filter(|s| !matches!(s.state, ConnectionState::Failed | ConnectionState::Stopped))
+        .map(|s| (s.platform, s.account_name))
+        .collect();
+    for (platform, account) in &state.runtimes {
+        if running.iter().any(|(p, a)| p == platform && a == account) {
+            continue;
+        }
+        if let Err(e) = ctx.platform_manager.start_platform_runtime(platform, account).await {
+            error!("Failed to restore {} runtime for '{}': {:?}", platform, account, e);
+        }
+    }
 }
 
 /// A wrapper for PluginManager that implements all the BotApi traits
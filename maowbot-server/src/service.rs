@@ -0,0 +1,121 @@
+//! maowbot-server/src/service.rs
+//!
+//! `--mode service`: runs the server under the Windows Service Control Manager
+//! (registered by `system install-service`). On Linux the systemd unit runs
+//! plain `--mode server` and stops it with SIGINT, so nothing here is needed.
+
+use std::sync::{Arc, OnceLock};
+use maowbot_core::eventbus::EventBus;
+
+/// The running server's event bus, so a stop request from the service manager
+/// can trigger the normal graceful shutdown.
+static EVENT_BUS: OnceLock<Arc<EventBus>> = OnceLock::new();
+
+pub fn attach_event_bus(event_bus: Arc<EventBus>) {
+    let _ = EVENT_BUS.set(event_bus);
+}
+
+#[cfg(windows)]
+pub use windows_service::{report_stopped, run};
+
+#[cfg(not(windows))]
+pub fn run(_args: crate::Args) -> Result<(), maowbot_core::Error> {
+    Err(maowbot_core::Error::Internal(
+        "--mode service is only used by the Windows service manager; use --mode server".into(),
+    ))
+}
+
+#[cfg(not(windows))]
+pub fn report_stopped() {}
+
+#[cfg(windows)]
+mod windows_service {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::OnceLock;
+    use tracing::{error, info};
+    use windows::core::{w, PWSTR};
+    use windows::Win32::System::Services::{
+        RegisterServiceCtrlHandlerW, SetServiceStatus, StartServiceCtrlDispatcherW,
+        SERVICE_ACCEPT_SHUTDOWN, SERVICE_ACCEPT_STOP, SERVICE_CONTROL_SHUTDOWN, SERVICE_CONTROL_STOP,
+        SERVICE_RUNNING, SERVICE_STATUS, SERVICE_STATUS_CURRENT_STATE, SERVICE_STATUS_HANDLE,
+        SERVICE_STOPPED, SERVICE_STOP_PENDING, SERVICE_TABLE_ENTRYW, SERVICE_WIN32_OWN_PROCESS,
+    };
+
+    use maowbot_core::Error;
+    use super::EVENT_BUS;
+    use crate::Args;
+
+    /// Handed from `run` to the service main thread the SCM starts.
+    static SERVICE_ARGS: OnceLock<(Args, tokio::runtime::Handle)> = OnceLock::new();
+    /// SERVICE_STATUS_HANDLE as an integer (raw handles aren't Sync)
+    static STATUS_HANDLE: AtomicUsize = AtomicUsize::new(0);
+
+    /// Connects to the SCM and blocks until the service stops. Must be called
+    /// from a blocking thread inside the tokio runtime.
+    pub fn run(args: Args) -> Result<(), Error> {
+        let _ = SERVICE_ARGS.set((args, tokio::runtime::Handle::current()));
+        let table = [
+            SERVICE_TABLE_ENTRYW {
+                lpServiceName: PWSTR(w!("maowbot").as_ptr() as *mut u16),
+                lpServiceProc: Some(service_main),
+            },
+            SERVICE_TABLE_ENTRYW::default(),
+        ];
+        unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) }
+            .map_err(|e| Error::Internal(format!("Not started by the service manager: {}", e)))
+    }
+
+    unsafe extern "system" fn service_main(_argc: u32, _argv: *mut PWSTR) {
+        let handle = match unsafe { RegisterServiceCtrlHandlerW(w!("maowbot"), Some(control_handler)) } {
+            Ok(h) => h,
+            Err(e) => {
+                error!("Failed to register service control handler: {}", e);
+                return;
+            }
+        };
+        STATUS_HANDLE.store(handle.0 as usize, Ordering::SeqCst);
+        set_state(SERVICE_RUNNING);
+        info!("Running as a Windows service");
+
+        if let Some((args, rt)) = SERVICE_ARGS.get() {
+            if let Err(e) = rt.block_on(crate::server::run_server(args.clone())) {
+                error!("Server error: {:?}", e);
+            }
+        }
+        report_stopped();
+    }
+
+    unsafe extern "system" fn control_handler(control: u32) {
+        if control == SERVICE_CONTROL_STOP || control == SERVICE_CONTROL_SHUTDOWN {
+            info!("Service stop requested");
+            set_state(SERVICE_STOP_PENDING);
+            if let Some(bus) = EVENT_BUS.get() {
+                bus.shutdown();
+            }
+        }
+    }
+
+    /// Tells the SCM the service has stopped; the server calls this right before exiting.
+    pub fn report_stopped() {
+        set_state(SERVICE_STOPPED);
+    }
+
+    fn set_state(state: SERVICE_STATUS_CURRENT_STATE) {
+        let raw = STATUS_HANDLE.load(Ordering::SeqCst);
+        if raw == 0 {
+            return;
+        }
+        let status = SERVICE_STATUS {
+            dwServiceType: SERVICE_WIN32_OWN_PROCESS,
+            dwCurrentState: state,
+            dwControlsAccepted: if state == SERVICE_RUNNING { SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN } else { 0 },
+            dwWin32ExitCode: 0,
+            dwServiceSpecificExitCode: 0,
+            dwCheckPoint: 0,
+            dwWaitHint: if state == SERVICE_STOP_PENDING { 60_000 } else { 0 },
+        };
+        unsafe {
+            let _ = SetServiceStatus(SERVICE_STATUS_HANDLE(raw as *mut _), &status);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! maowbot-server/src/startup.rs
+//!
+//! Startup order and readiness. Each subsystem starts only once the ones it
+//! depends on are up (ready, or degraded but usable), so a failure stops
+//! everything downstream of it instead of letting it start half-wired. The
+//! gRPC listener comes up early so `HealthService` can report progress, but
+//! every other service answers `Unavailable` until startup has finished.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use http::{Request, Response};
+use tonic::body::BoxBody;
+use tonic::Status;
+use tower::{Layer, Service};
+use tracing::{error, info, warn};
+
+use crate::authz::permissions::split_path;
+
+/// The service that answers while everything else is still starting.
+pub const HEALTH_SERVICE: &str = "maowbot.services.HealthService";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    /// Postgres running and migrated
+    Database,
+    /// Repositories and the settings loaded through them
+    Repositories,
+    /// API tokens and platform credentials, refreshed
+    Auth,
+    /// Autostarted platform runtimes and the services built on them
+    Platforms,
+    /// Plugins handed the bot API
+    Plugins,
+    /// gRPC services (other than health) and the HTTP control API
+    Grpc,
+}
+
+impl Subsystem {
+    /// Every subsystem, in an order that satisfies `depends_on`.
+    pub const ALL: [Subsystem; 6] = [
+        Subsystem::Database,
+        Subsystem::Repositories,
+        Subsystem::Auth,
+        Subsystem::Platforms,
+        Subsystem::Plugins,
+        Subsystem::Grpc,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Subsystem::Database => "database",
+            Subsystem::Repositories => "repositories",
+            Subsystem::Auth => "auth",
+            Subsystem::Platforms => "platforms",
+            Subsystem::Plugins => "plugins",
+            Subsystem::Grpc => "grpc",
+        }
+    }
+
+    pub fn depends_on(self) -> &'static [Subsystem] {
+        match self {
+            Subsystem::Database => &[],
+            Subsystem::Repositories => &[Subsystem::Database],
+            Subsystem::Auth => &[Subsystem::Repositories],
+            Subsystem::Platforms => &[Subsystem::Repositories, Subsystem::Auth],
+            Subsystem::Plugins => &[Subsystem::Platforms],
+            Subsystem::Grpc => &[Subsystem::Auth, Subsystem::Plugins],
+        }
+    }
+}
+
+impl fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Readiness {
+    Pending,
+    Starting,
+    Ready,
+    /// Started, but with errors; dependents still start
+    Degraded,
+    /// Didn't start; nothing that depends on it starts either
+    Failed,
+}
+
+impl Readiness {
+    /// Whether dependents may start.
+    pub fn is_up(self) -> bool {
+        matches!(self, Readiness::Ready | Readiness::Degraded)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubsystemStatus {
+    pub state: Readiness,
+    pub message: String,
+    /// When the state last changed
+    pub since: DateTime<Utc>,
+    /// How long it took to start, once it has
+    pub took: Option<Duration>,
+}
+
+struct Entry {
+    status: SubsystemStatus,
+    started: Option<Instant>,
+}
+
+pub struct Startup {
+    entries: Mutex<HashMap<Subsystem, Entry>>,
+    /// Set once every subsystem is up; read on every gRPC request
+    ready: AtomicBool,
+    started: Instant,
+}
+
+impl Default for Startup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Startup {
+    pub fn new() -> Self {
+        let now = Utc::now();
+        let entries = Subsystem::ALL.iter()
+            .map(|s| (*s, Entry {
+                status: SubsystemStatus { state: Readiness::Pending, message: String::new(), since: now, took: None },
+                started: None,
+            }))
+            .collect();
+        Self { entries: Mutex::new(entries), ready: AtomicBool::new(false), started: Instant::now() }
+    }
+
+    /// Marks `subsystem` as starting, or as failed when one of its
+    /// dependencies isn't up; the caller skips starting it in that case.
+    pub fn begin(&self, subsystem: Subsystem) -> Result<(), String> {
+        let mut entries = self.lock();
+        let blocked = subsystem.depends_on().iter()
+            .find(|dep| !entries[*dep].status.state.is_up());
+        if let Some(dep) = blocked {
+            let reason = format!("needs {}, which is {}", dep, describe(entries[dep].status.state));
+            error!("Not starting {}: {}", subsystem, reason);
+            set(&mut entries, subsystem, Readiness::Failed, reason.clone());
+            return Err(reason);
+        }
+        info!("Starting {}...", subsystem);
+        set(&mut entries, subsystem, Readiness::Starting, String::new());
+        Ok(())
+    }
+
+    pub fn ready(&self, subsystem: Subsystem, message: impl Into<String>) {
+        self.finish(subsystem, Readiness::Ready, message.into());
+    }
+
+    pub fn degraded(&self, subsystem: Subsystem, message: impl Into<String>) {
+        self.finish(subsystem, Readiness::Degraded, message.into());
+    }
+
+    // Subsystems report trouble as degraded today; only the tests mark one failed
+    #[cfg(test)]
+    pub fn failed(&self, subsystem: Subsystem, message: impl Into<String>) {
+        self.finish(subsystem, Readiness::Failed, message.into());
+    }
+
+    fn finish(&self, subsystem: Subsystem, state: Readiness, message: String) {
+        let mut entries = self.lock();
+        match state {
+            Readiness::Failed => error!("{} failed to start: {}", subsystem, message),
+            Readiness::Degraded => warn!("{} started with errors: {}", subsystem, message),
+            _ => info!("{} ready", subsystem),
+        }
+        set(&mut entries, subsystem, state, message);
+        if Subsystem::ALL.iter().all(|s| entries[s].status.state.is_up()) {
+            self.ready.store(true, Ordering::Release);
+            info!("Startup finished in {:.1}s", self.started.elapsed().as_secs_f64());
+        }
+    }
+
+    /// Whether every subsystem is up.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Every subsystem's status, in startup order.
+    pub fn snapshot(&self) -> Vec<(Subsystem, SubsystemStatus)> {
+        let entries = self.lock();
+        Subsystem::ALL.iter().map(|s| (*s, entries[s].status.clone())).collect()
+    }
+
+    /// The first subsystem that isn't up yet, for "still starting" messages.
+    pub fn waiting_on(&self) -> Option<(Subsystem, Readiness)> {
+        let entries = self.lock();
+        Subsystem::ALL.iter()
+            .find(|s| !entries[*s].status.state.is_up())
+            .map(|s| (*s, entries[s].status.state))
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<Subsystem, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn set(entries: &mut HashMap<Subsystem, Entry>, subsystem: Subsystem, state: Readiness, message: String) {
+    let entry = entries.get_mut(&subsystem).expect("every subsystem has an entry");
+    match state {
+        Readiness::Starting => entry.started = Some(Instant::now()),
+        Readiness::Ready | Readiness::Degraded => entry.status.took = entry.started.map(|t| t.elapsed()),
+        _ => {}
+    }
+    entry.status.state = state;
+    entry.status.message = message;
+    entry.status.since = Utc::now();
+}
+
+fn describe(state: Readiness) -> &'static str {
+    match state {
+        Readiness::Pending => "not started",
+        Readiness::Starting => "still starting",
+        Readiness::Ready => "ready",
+        Readiness::Degraded => "degraded",
+        Readiness::Failed => "failed",
+    }
+}
+
+/// Tower layer that answers every gRPC request except the health service's
+/// with `Unavailable` until startup has finished.
+#[derive(Clone)]
+pub struct ReadinessGateLayer {
+    startup: Arc<Startup>,
+}
+
+impl ReadinessGateLayer {
+    pub fn new(startup: Arc<Startup>) -> Self {
+        Self { startup }
+    }
+}
+
+impl<S> Layer<S> for ReadinessGateLayer {
+    type Service = ReadinessGate<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReadinessGate { inner, startup: self.startup.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReadinessGate<S> {
+    inner: S,
+    startup: Arc<Startup>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for ReadinessGate<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        // Use the instance that was polled ready and leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let is_health = split_path(req.uri().path()).is_some_and(|(service, _)| service == HEALTH_SERVICE);
+        if is_health || self.startup.is_ready() {
+            return Box::pin(inner.call(req));
+        }
+        let reason = match self.startup.waiting_on() {
+            Some((subsystem, Readiness::Failed)) => format!("{} failed to start", subsystem),
+            Some((subsystem, _)) => format!("waiting on {}", subsystem),
+            None => "finishing".to_string(),
+        };
+        let status = Status::unavailable(format!(
+            "The server is still starting ({}); retry shortly or connect with --wait-ready", reason
+        ));
+        Box::pin(async move { Ok(status.into_http()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_is_in_dependency_order() {
+        for (i, subsystem) in Subsystem::ALL.iter().enumerate() {
+            for dep in subsystem.depends_on() {
+                let dep_index = Subsystem::ALL.iter().position(|s| s == dep).unwrap();
+                assert!(dep_index < i, "{} starts before its dependency {}", subsystem, dep);
+            }
+        }
+    }
+
+    #[test]
+    fn test_failures_block_dependents_and_readiness() {
+        let startup = Startup::new();
+        assert!(startup.begin(Subsystem::Repositories).is_err());
+        assert_eq!(startup.snapshot()[1].1.state, Readiness::Failed);
+
+        let startup = Startup::new();
+        for subsystem in [Subsystem::Database, Subsystem::Repositories, Subsystem::Auth] {
+            startup.begin(subsystem).unwrap();
+            startup.ready(subsystem, "");
+        }
+        startup.begin(Subsystem::Platforms).unwrap();
+        startup.degraded(Subsystem::Platforms, "autostart failed");
+        startup.begin(Subsystem::Plugins).unwrap();
+        startup.failed(Subsystem::Plugins, "boom");
+        assert!(startup.begin(Subsystem::Grpc).is_err());
+        assert_eq!(startup.waiting_on(), Some((Subsystem::Plugins, Readiness::Failed)));
+        assert!(!startup.is_ready());
+
+        let startup = Startup::new();
+        for subsystem in Subsystem::ALL {
+            startup.begin(subsystem).unwrap();
+            startup.ready(subsystem, "");
+        }
+        assert!(startup.is_ready());
+        assert!(startup.snapshot().iter().all(|(_, s)| s.took.is_some()));
+    }
+}
@@ -0,0 +1,343 @@
+//! maowbot-server/src/tls.rs
+//!
+//! TLS material for the gRPC server. A local CA in `certs/` signs the server
+//! certificate (SANs: localhost, this machine's host name and interface
+//! addresses, plus `tls.extra_sans`) and the client certificates required when
+//! `tls.require_client_cert` is on. Remote GUI/TUI clients only need `ca.crt`.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tonic::transport::{Certificate as TlsCertificate, Identity, ServerTlsConfig};
+use tracing::{info, warn};
+
+use maowbot_core::settings::SettingsRegistry;
+use maowbot_core::Error;
+
+pub const CA_CERT_PATH: &str = "certs/ca.crt";
+pub const CA_KEY_PATH: &str = "certs/ca.key";
+/// Server certificate followed by the CA, so clients that trust this file keep working.
+pub const SERVER_CERT_PATH: &str = "certs/server.crt";
+pub const SERVER_KEY_PATH: &str = "certs/server.key";
+pub const SERVER_INFO_PATH: &str = "certs/server.json";
+/// Client certificate for GUI/TUI processes started on this machine.
+pub const LOCAL_CLIENT_CERT_PATH: &str = "certs/client.crt";
+pub const LOCAL_CLIENT_KEY_PATH: &str = "certs/client.key";
+
+const CA_COMMON_NAME: &str = "MaowBot Local CA";
+
+/// Fingerprint of the certificate the running server was started with.
+static ACTIVE_FINGERPRINT: OnceLock<String> = OnceLock::new();
+
+/// Written next to the server certificate so we can tell what it covers
+/// without parsing X.509.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerCertInfo {
+    pub sans: Vec<String>,
+    pub generated_at: DateTime<Utc>,
+    pub fingerprint: String,
+}
+
+impl ServerCertInfo {
+    /// True if the server was started with a different certificate.
+    pub fn restart_required(&self) -> bool {
+        ACTIVE_FINGERPRINT.get().is_some_and(|active| *active != self.fingerprint)
+    }
+}
+
+pub struct IssuedClientCert {
+    pub cert_pem: String,
+    pub key_pem: String,
+    pub ca_pem: String,
+    pub fingerprint: String,
+}
+
+pub fn has_ca() -> bool {
+    Path::new(CA_CERT_PATH).exists() && Path::new(CA_KEY_PATH).exists()
+}
+
+pub fn server_cert_info() -> Option<ServerCertInfo> {
+    let raw = fs::read_to_string(SERVER_INFO_PATH).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Splits a `tls.extra_sans` value ("host.lan, 192.168.1.20") into names.
+pub fn parse_sans(raw: &str) -> Vec<String> {
+    raw.split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Everything the server certificate should be valid for.
+pub fn server_sans(settings: &SettingsRegistry) -> Vec<String> {
+    let mut sans = vec!["localhost".to_string(), "127.0.0.1".to_string(), "::1".to_string()];
+    if let Some(host) = local_hostname() {
+        sans.push(host);
+    }
+    if let Ok(ifaces) = if_addrs::get_if_addrs() {
+        for iface in ifaces {
+            let ip = iface.ip();
+            if ip.is_loopback() || iface.is_link_local() {
+                continue;
+            }
+            sans.push(ip.to_string());
+        }
+    }
+    if let Some(extra) = settings.get("tls.extra_sans") {
+        sans.extend(parse_sans(&extra));
+    }
+    let mut seen = std::collections::HashSet::new();
+    sans.retain(|s| seen.insert(s.to_ascii_lowercase()));
+    sans
+}
+
+fn local_hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_ascii_lowercase())
+        .filter(|h| !h.is_empty())
+}
+
+/// Builds the server's TLS config, creating or re-issuing certificates as needed.
+///
+/// - No certificates yet: create the CA and a server certificate.
+/// - CA present and the SAN list changed (new LAN address, edited
+///   `tls.extra_sans`): re-issue the server certificate from the same CA.
+/// - Legacy self-signed certificate without a CA: used as-is, unless mutual
+///   TLS is required, which needs the CA.
+pub fn server_tls_config(settings: &SettingsRegistry) -> Result<ServerTlsConfig, Error> {
+    restrict_private_keys()?;
+    let require_client_cert = settings.get_bool("tls.require_client_cert").unwrap_or(false);
+    let sans = server_sans(settings);
+    let have_server_cert = Path::new(SERVER_CERT_PATH).exists() && Path::new(SERVER_KEY_PATH).exists();
+
+    if !have_server_cert || (!has_ca() && require_client_cert) {
+        info!("Generating gRPC TLS certificates in certs/");
+        generate_server_cert(sans)?;
+    } else if has_ca() {
+        let covered = server_cert_info()
+            .map(|info| sans.iter().all(|s| info.sans.iter().any(|c| c.eq_ignore_ascii_case(s))))
+            .unwrap_or(false);
+        if !covered {
+            info!("Server certificate does not cover {:?}; re-issuing it from the local CA", sans);
+            if let Err(e) = generate_server_cert(sans) {
+                warn!("Could not re-issue the server certificate, keeping the old one: {}", e);
+            }
+        }
+    }
+
+    let cert_pem = fs::read(SERVER_CERT_PATH)?;
+    let key_pem = fs::read(SERVER_KEY_PATH)?;
+    let _ = ACTIVE_FINGERPRINT.set(
+        server_cert_info().map(|i| i.fingerprint).unwrap_or_default(),
+    );
+
+    let mut tls = ServerTlsConfig::new().identity(Identity::from_pem(cert_pem, key_pem));
+    if require_client_cert {
+        let ca_pem = fs::read(CA_CERT_PATH)?;
+        tls = tls.client_ca_root(TlsCertificate::from_pem(ca_pem));
+        info!("Mutual TLS enabled: clients must present a certificate issued by {}", CA_CERT_PATH);
+    } else if !has_ca() {
+        warn!("Using a legacy self-signed certificate; run `system cert regenerate` to switch to the local CA");
+    }
+    Ok(tls)
+}
+
+/// Issues a new server certificate for `sans` from the local CA (creating the
+/// CA first if needed). Takes effect on the next server start.
+pub fn generate_server_cert(sans: Vec<String>) -> Result<ServerCertInfo, Error> {
+    let (ca_cert, ca_key) = load_or_create_ca()?;
+
+    let mut params = CertificateParams::new(sans.clone())?;
+    params.distinguished_name = DistinguishedName::new();
+    params.distinguished_name.push(DnType::CommonName, "maowbot-server");
+    params.key_usages = vec![KeyUsagePurpose::DigitalSignature, KeyUsagePurpose::KeyEncipherment];
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+
+    let key = KeyPair::generate()?;
+    let cert = params.signed_by(&key, &ca_cert, &ca_key)?;
+    let ca_pem = fs::read_to_string(CA_CERT_PATH)?;
+
+    let info = ServerCertInfo {
+        sans,
+        generated_at: Utc::now(),
+        fingerprint: fingerprint(cert.der()),
+    };
+    write_file(SERVER_KEY_PATH, &key.serialize_pem(), true)?;
+    write_file(SERVER_CERT_PATH, &format!("{}{}", cert.pem(), ca_pem), false)?;
+    write_file(SERVER_INFO_PATH, &serde_json::to_string_pretty(&info)?, false)?;
+    Ok(info)
+}
+
+/// Issues a client certificate for mutual TLS. The key is not stored.
+pub fn issue_client_cert(name: &str) -> Result<IssuedClientCert, Error> {
+    if name.is_empty()
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(Error::ValidationError(
+            "Client name may only contain letters, digits, '-', '_' and '.'".into(),
+        ));
+    }
+    if !has_ca() {
+        return Err(Error::NotFound(
+            "No local CA yet; run `system cert regenerate` first".into(),
+        ));
+    }
+    let (ca_cert, ca_key) = load_or_create_ca()?;
+    let (cert, key) = sign_client_cert(name, &ca_cert, &ca_key)?;
+    Ok(IssuedClientCert {
+        fingerprint: fingerprint(cert.der()),
+        cert_pem: cert.pem(),
+        key_pem: key.serialize_pem(),
+        ca_pem: fs::read_to_string(CA_CERT_PATH)?,
+    })
+}
+
+fn sign_client_cert(name: &str, ca_cert: &Certificate, ca_key: &KeyPair) -> Result<(Certificate, KeyPair), Error> {
+    let mut params = CertificateParams::new(Vec::<String>::new())?;
+    params.distinguished_name = DistinguishedName::new();
+    params.distinguished_name.push(DnType::CommonName, name);
+    params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+    let key = KeyPair::generate()?;
+    let cert = params.signed_by(&key, ca_cert, ca_key)?;
+    Ok((cert, key))
+}
+
+fn ca_params() -> CertificateParams {
+    let mut params = CertificateParams::default();
+    params.distinguished_name = DistinguishedName::new();
+    params.distinguished_name.push(DnType::CommonName, CA_COMMON_NAME);
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    params
+}
+
+/// Returns the CA used for signing. rcgen can't parse `ca.crt` back, so the
+/// issuer is rebuilt from the stored key and the fixed subject; certificates
+/// it signs chain to the original `ca.crt` because both match.
+///
+/// Creating the CA also issues the local client certificate, so GUI/TUI
+/// processes on this machine keep working when mutual TLS is turned on.
+fn load_or_create_ca() -> Result<(Certificate, KeyPair), Error> {
+    if has_ca() {
+        let key = KeyPair::from_pem(&fs::read_to_string(CA_KEY_PATH)?)?;
+        let cert = ca_params().self_signed(&key)?;
+        return Ok((cert, key));
+    }
+
+    info!("Creating local certificate authority in {}", CA_CERT_PATH);
+    let key = KeyPair::generate()?;
+    let cert = ca_params().self_signed(&key)?;
+    write_file(CA_KEY_PATH, &key.serialize_pem(), true)?;
+    write_file(CA_CERT_PATH, &cert.pem(), false)?;
+
+    let (client_cert, client_key) = sign_client_cert("maowbot-local", &cert, &key)?;
+    write_file(LOCAL_CLIENT_KEY_PATH, &client_key.serialize_pem(), true)?;
+    write_file(LOCAL_CLIENT_CERT_PATH, &client_cert.pem(), false)?;
+
+    Ok((cert, key))
+}
+
+fn fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Writes `contents` to a temp file and renames it over `path`, so a crash
+/// never leaves a half-written file. When `private` the temp file is
+/// owner-only (Unix) from the moment it exists, whatever the old file allowed.
+/// Keys are written before their certificates.
+fn write_file(path: &str, contents: &str, private: bool) -> Result<(), Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // A leftover temp file is removed first: its permissions can't be trusted.
+    let tmp = format!("{}.tmp", path);
+    match fs::remove_file(&tmp) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        if private {
+            options.mode(0o600);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let mut file = options.open(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Makes the stored private keys owner-only (Unix). Older versions wrote
+/// `server.key` with default permissions, and a key that's never re-issued
+/// would otherwise keep them.
+fn restrict_private_keys() -> Result<(), Error> {
+    #[cfg(unix)]
+    for path in [CA_KEY_PATH, SERVER_KEY_PATH, LOCAL_CLIENT_KEY_PATH] {
+        use std::os::unix::fs::PermissionsExt;
+        if Path::new(path).exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_extra_sans() {
+        assert_eq!(
+            parse_sans(" bot.lan, 192.168.1.20  10.0.0.5,,"),
+            vec!["bot.lan", "192.168.1.20", "10.0.0.5"]
+        );
+        assert!(parse_sans("").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_files_are_owner_only_over_an_old_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("maowbot-tls-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("server.key");
+        let path = path.to_str().unwrap();
+        for old in [path.to_string(), format!("{}.tmp", path)] {
+            fs::write(&old, "old").unwrap();
+            fs::set_permissions(&old, fs::Permissions::from_mode(0o644)).unwrap();
+        }
+
+        write_file(path, "new key", true).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "new key");
+        assert_eq!(fs::metadata(path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert!(!Path::new(&format!("{}.tmp", path)).exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
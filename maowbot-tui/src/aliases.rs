@@ -0,0 +1,241 @@
+// User-defined command aliases / macros, persisted in ~/.maowbot_tui_aliases
+//
+// File format is one alias per line, the same form accepted at the prompt:
+//
+//     # comments are ignored
+//     brb = connection stop twitch-irc mybot; osc set AFK true
+//
+// An expansion may contain several `;`-separated commands. `$1`..`$9` are
+// replaced with the arguments given when the alias is run and `$*` with all of
+// them; when no placeholder is used, the arguments are appended to the last
+// command.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::script::split_commands;
+
+/// Nested aliases deeper than this are assumed to be a cycle
+const MAX_EXPANSION_DEPTH: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct AliasStore {
+    path: Option<PathBuf>,
+    aliases: BTreeMap<String, String>,
+}
+
+impl AliasStore {
+    /// An empty store that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load from `~/.maowbot_tui_aliases`, starting empty if the file is missing or unreadable.
+    pub fn load_default() -> Self {
+        match dirs::home_dir() {
+            Some(mut path) => {
+                path.push(".maowbot_tui_aliases");
+                Self::load(&path)
+            }
+            None => Self::in_memory(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Self {
+        let mut store = Self {
+            path: Some(path.to_path_buf()),
+            aliases: BTreeMap::new(),
+        };
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            for line in contents.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                match parse_definition(line) {
+                    Ok((name, expansion)) => {
+                        store.aliases.insert(name, expansion);
+                    }
+                    Err(e) => tracing::warn!("Ignoring alias line in {}: {}", path.display(), e),
+                }
+            }
+        }
+        store
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut contents = String::from("# MaowBot TUI aliases - edit with 'alias add/remove'\n");
+        for (name, expansion) in &self.aliases {
+            contents.push_str(&format!("{} = {}\n", name, expansion));
+        }
+        std::fs::write(path, contents).map_err(|e| format!("Failed to save aliases to {}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.aliases.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.aliases.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Add or replace an alias and persist the store. Returns the previous expansion, if any.
+    pub fn set(&mut self, name: &str, expansion: &str) -> Result<Option<String>, String> {
+        validate_name(name)?;
+        let expansion = expansion.trim();
+        if expansion.is_empty() {
+            return Err("Alias expansion cannot be empty".to_string());
+        }
+        let previous = self.aliases.insert(name.to_lowercase(), expansion.to_string());
+        self.save()?;
+        Ok(previous)
+    }
+
+    /// Remove an alias and persist the store. Returns false if it did not exist.
+    pub fn remove(&mut self, name: &str) -> Result<bool, String> {
+        let removed = self.aliases.remove(&name.to_lowercase()).is_some();
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Expand `line` into the commands to run, following nested aliases.
+    ///
+    /// Returns `Ok(None)` when the first word is not an alias.
+    pub fn expand(&self, line: &str) -> Result<Option<Vec<String>>, String> {
+        let first = line.split_whitespace().next().unwrap_or("");
+        if self.get(first).is_none() {
+            return Ok(None);
+        }
+        let mut out = Vec::new();
+        self.expand_into(line, 0, &mut out)?;
+        Ok(Some(out))
+    }
+
+    fn expand_into(&self, line: &str, depth: usize, out: &mut Vec<String>) -> Result<(), String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some(expansion) = words.first().and_then(|w| self.get(w)) else {
+            out.push(line.to_string());
+            return Ok(());
+        };
+        if depth >= MAX_EXPANSION_DEPTH {
+            return Err(format!("Alias '{}' nests too deeply (is it recursive?)", words[0]));
+        }
+
+        for (_, command) in split_commands(&substitute_args(expansion, &words[1..])) {
+            self.expand_into(&command, depth + 1, out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse `name = expansion`.
+pub fn parse_definition(text: &str) -> Result<(String, String), String> {
+    let (name, expansion) = text
+        .split_once('=')
+        .ok_or_else(|| "Expected 'name = command'".to_string())?;
+    let name = name.trim();
+    validate_name(name)?;
+    let expansion = unquote(expansion.trim());
+    if expansion.is_empty() {
+        return Err(format!("Alias '{}' has an empty expansion", name));
+    }
+    Ok((name.to_lowercase(), expansion.to_string()))
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Alias name cannot be empty".to_string());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid alias name '{}': use letters, digits, '-' or '_'", name));
+    }
+    Ok(())
+}
+
+fn unquote(text: &str) -> &str {
+    for q in ['"', '\''] {
+        if text.len() >= 2 && text.starts_with(q) && text.ends_with(q) {
+            return &text[1..text.len() - 1];
+        }
+    }
+    text
+}
+
+/// Replace `$1`..`$9` and `$*` with `args`, or append the args when no placeholder is used.
+fn substitute_args(expansion: &str, args: &[&str]) -> String {
+    let has_placeholder = expansion.contains("$*")
+        || (1..=9).any(|n| expansion.contains(&format!("${}", n)));
+    if !has_placeholder {
+        return if args.is_empty() {
+            expansion.to_string()
+        } else {
+            format!("{} {}", expansion, args.join(" "))
+        };
+    }
+
+    let mut result = expansion.replace("$*", &args.join(" "));
+    for n in (1..=9).rev() {
+        let value = args.get(n - 1).copied().unwrap_or("");
+        result = result.replace(&format!("${}", n), value);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(defs: &[&str]) -> AliasStore {
+        let mut store = AliasStore::in_memory();
+        for def in defs {
+            let (name, expansion) = parse_definition(def).unwrap();
+            store.set(&name, &expansion).unwrap();
+        }
+        store
+    }
+
+    #[test]
+    fn test_parse_definition() {
+        assert_eq!(
+            parse_definition("brb = connection stop twitch-irc mybot; osc set AFK true").unwrap(),
+            ("brb".to_string(), "connection stop twitch-irc mybot; osc set AFK true".to_string())
+        );
+        assert_eq!(parse_definition("Hi = \"twitch msg hi\"").unwrap().1, "twitch msg hi");
+        assert!(parse_definition("no equals sign").is_err());
+        assert!(parse_definition("bad name = status").is_err());
+        assert!(parse_definition("empty =").is_err());
+    }
+
+    #[test]
+    fn test_expand_multiple_commands() {
+        let aliases = store(&["brb = connection stop twitch-irc mybot; osc set AFK true"]);
+        assert_eq!(
+            aliases.expand("brb").unwrap().unwrap(),
+            vec!["connection stop twitch-irc mybot", "osc set AFK true"]
+        );
+        assert_eq!(aliases.expand("status").unwrap(), None);
+    }
+
+    #[test]
+    fn test_expand_arguments() {
+        let aliases = store(&["say = twitch msg $1 $*", "tm = twitch msg"]);
+        assert_eq!(aliases.expand("say chan hello").unwrap().unwrap(), vec!["twitch msg chan chan hello"]);
+        assert_eq!(aliases.expand("tm hello there").unwrap().unwrap(), vec!["twitch msg hello there"]);
+    }
+
+    #[test]
+    fn test_nested_and_recursive() {
+        let aliases = store(&["a = b; status", "b = list"]);
+        assert_eq!(aliases.expand("a").unwrap().unwrap(), vec!["list", "status"]);
+
+        let looping = store(&["x = y", "y = x"]);
+        assert!(looping.expand("x").is_err());
+    }
+}
@@ -9,7 +9,6 @@ use uuid::Uuid;
 
 use maowbot_common::models::auth::Platform;
 use maowbot_common::models::platform::PlatformCredential;
-use maowbot_common::models::redeem::Redeem;
 use maowbot_common::models::user::User;
 use maowbot_common::traits::api::BotApi;
 use maowbot_core::Error;
@@ -224,7 +223,6 @@ async fn account_add_flow(platform: Platform, typed_name: &str, bot_api: &Arc<dy
 
         // 1) Make twitch-irc
         let irc_res = do_oauth_like_flow_and_return_cred(Platform::TwitchIRC, user.user_id, false, bot_api).await;
-        let mut maybe_irc_cred: Option<PlatformCredential> = None;
         if let Ok(mut irc_cred) = irc_res {
             irc_cred.is_broadcaster = is_broadcaster;
             irc_cred.is_teammate    = is_teammate;
@@ -233,7 +231,6 @@ async fn account_add_flow(platform: Platform, typed_name: &str, bot_api: &Arc<dy
                 println!("(Warning) Could not store updated Twitch-IRC flags => {e}");
             } else {
                 println!("Created twitch-irc credentials.\n");
-                maybe_irc_cred = Some(irc_cred);
             }
         } else if let Err(e) = irc_res {
             println!("(Warning) Could not create twitch-irc => {e}");
@@ -554,7 +551,6 @@ async fn prompt_discord_bot_token_and_fetch() -> Result<HashMap<String, String>,
     struct DiscordMe {
         id: String,
         username: String,
-        discriminator: String,
     }
 
     println!("\nDiscord flow => we’ll ask for your Bot token, fetch /users/@me, and then your Application ID.\n");
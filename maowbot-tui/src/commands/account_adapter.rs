@@ -3,21 +3,23 @@ use maowbot_common_ui::{GrpcClient, commands::account::AccountCommands};
 use std::io::{Write, stdin, stdout};
 use std::collections::HashMap;
 use maowbot_proto::maowbot::services::{
-    BeginAuthFlowRequest, CompleteAuthFlowRequest, ListCredentialsRequest,
-    credential_service_client::CredentialServiceClient,
+    BeginAuthFlowRequest, CompleteAuthFlowRequest, ListCredentialsRequest, GetScopeStatusResponse,
     complete_auth_flow_request::{self, AuthData}
 };
 use maowbot_proto::maowbot::common::Platform;
 
 pub async fn handle_account_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
-        return "Usage: account <add|remove|list|show|refresh|type> [platform] [usernameOrUUID]".to_string();
+        return "Usage: account <add|remove|list|show|refresh|type|scopes|reauth> [platform] [usernameOrUUID]".to_string();
     }
 
     match args[0] {
         "add" => {
+            // --device: log in with a short code instead of a browser callback (headless hosts)
+            let device = args.contains(&"--device");
+            let args: Vec<&str> = args.iter().copied().filter(|a| *a != "--device").collect();
             if args.len() < 3 {
-                return "Usage: account add <platform> <typed_global_username>".to_string();
+                return "Usage: account add <platform> <typed_global_username> [--device]".to_string();
             }
             let platform_str = args[1];
             let typed_name = args[2];
@@ -108,11 +110,11 @@ pub async fn handle_account_command(args: &[&str], client: &GrpcClient) -> Strin
             
             // Do the actual auth flow based on platform
             let result = if platform == Platform::Vrchat {
-                vrchat_add_flow(client, platform, user_id.clone()).await
+                vrchat_add_flow(client, platform, user_id.clone(), None).await
             } else if platform == Platform::Discord && is_bot {
                 discord_bot_add_flow(client, platform, user_id.clone()).await
             } else {
-                oauth_add_flow(client, platform, user_id.clone(), is_bot).await
+                oauth_add_flow(client, platform, user_id.clone(), is_bot, device).await
             };
             
             match result {
@@ -131,7 +133,7 @@ pub async fn handle_account_command(args: &[&str], client: &GrpcClient) -> Strin
                         println!("\nBecause this is a non-bot TwitchHelix account, also create matching:\n - TwitchIrc\n - TwitchEventSub\n");
                         
                         // Create TwitchIrc
-                        if let Ok(irc_id) = oauth_add_flow(client, Platform::TwitchIrc, user_id.clone(), false).await {
+                        if let Ok(irc_id) = oauth_add_flow(client, Platform::TwitchIrc, user_id.clone(), false, device).await {
                             let _ = update_credential_flags(client, &irc_id, false, is_broadcaster, is_teammate).await;
                             println!("Created TwitchIrc credentials.\n");
                         }
@@ -169,7 +171,7 @@ pub async fn handle_account_command(args: &[&str], client: &GrpcClient) -> Strin
         }
         
         "list" => {
-            let platform = args.get(1).map(|s| *s);
+            let platform = args.get(1).copied();
             
             match AccountCommands::list_accounts(client, platform).await {
                 Ok(result) => {
@@ -256,12 +258,124 @@ pub async fn handle_account_command(args: &[&str], client: &GrpcClient) -> Strin
             }
         }
         
-        _ => "Usage: account <add|remove|list|show|refresh|type> [platform] [usernameOrUUID]".to_string(),
+        "scopes" => {
+            let recheck = args.contains(&"--recheck");
+            match AccountCommands::scope_status(client, recheck).await {
+                Ok(status) => format_scope_status(&status),
+                Err(e) => format!("Error checking scopes: {}", e),
+            }
+        }
+
+        "reauth" => {
+            // Log an existing account in again so its token picks up scopes added since
+            let device = args.contains(&"--device");
+            let args: Vec<&str> = args.iter().copied().filter(|a| *a != "--device").collect();
+            if args.len() < 3 {
+                return "Usage: account reauth <platform> <usernameOrUUID> [--device]".to_string();
+            }
+            let cred = match AccountCommands::find_credential(client, args[1], args[2]).await {
+                Ok(cred) => cred,
+                Err(e) => return format!("Error finding account: {}", e),
+            };
+
+            // EventSub shares the Helix token, so that is the login to redo
+            let platform = match Platform::try_from(cred.platform).unwrap_or(Platform::Unknown) {
+                Platform::TwitchEventsub => Platform::TwitchHelix,
+                p => p,
+            };
+            if platform == Platform::Vrchat || (platform == Platform::Discord && cred.is_bot) {
+                return format!("{:?} accounts don't use OAuth scopes; use `account add` to replace the login.", platform);
+            }
+            println!("Logging '{}' in again on {:?}; approve every permission Twitch asks for.", cred.user_name, platform);
+
+            let credential_id = match oauth_add_flow(client, platform, cred.user_id.clone(), cred.is_bot, device).await {
+                Ok(id) => id,
+                Err(e) => return format!("Error logging in again: {}", e),
+            };
+            if let Err(e) = update_credential_flags(client, &credential_id, cred.is_bot, cred.is_broadcaster, cred.is_teammate).await {
+                return format!("Logged in again but failed to restore account flags: {}", e);
+            }
+            if platform == Platform::TwitchHelix && !cred.is_bot {
+                if let Err(e) = reuse_twitch_helix_for_eventsub(client, cred.user_id.clone()).await {
+                    println!("(Warning) Could not update TwitchEventSub => {}", e);
+                }
+            }
+
+            match AccountCommands::scope_status(client, true).await {
+                Ok(status) => format!("Logged in again.\n{}", format_scope_status(&status)),
+                Err(e) => format!("Logged in again, but could not recheck scopes: {}", e),
+            }
+        }
+
+        _ => "Usage: account <add|remove|list|show|refresh|type|scopes|reauth> [platform] [usernameOrUUID]".to_string(),
     }
 }
 
 // Helper functions
 
+fn scope_platform_name(platform: i32) -> &'static str {
+    match Platform::try_from(platform).unwrap_or(Platform::Unknown) {
+        Platform::TwitchIrc => "twitch-irc",
+        Platform::TwitchEventsub => "twitch-eventsub",
+        _ => "twitch",
+    }
+}
+
+fn format_scope_status(status: &GetScopeStatusResponse) -> String {
+    let Some(checked_at) = &status.checked_at else {
+        return "The startup scope check hasn't finished yet; try `account scopes --recheck`.".to_string();
+    };
+    let checked_at = chrono::DateTime::from_timestamp(checked_at.seconds, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default();
+    if status.credentials.is_empty() {
+        return format!("No Twitch credentials stored (checked {}).", checked_at);
+    }
+
+    let mut out = format!("Twitch token scopes (checked {}):\n", checked_at);
+    for cred in &status.credentials {
+        let state = if cred.missing_scopes.is_empty() {
+            "ok".to_string()
+        } else {
+            format!("missing {} (affects {})", cred.missing_scopes.join(", "), cred.affected_features.join(", "))
+        };
+        out.push_str(&format!(
+            "  {:<16} {:<20} {}{}\n",
+            scope_platform_name(cred.platform),
+            cred.user_name,
+            state,
+            if cred.is_broadcaster { " [broadcaster]" } else { "" },
+        ));
+        if !cred.verified {
+            out.push_str(&format!("      (stored scopes only: {})\n", cred.error_message));
+        }
+    }
+
+    if status.degraded_features.is_empty() {
+        out.push_str("No features are degraded.\n");
+    } else {
+        out.push_str("\nDegraded features:\n");
+        for feature in &status.degraded_features {
+            out.push_str(&format!(
+                "  {:<14} {} (needs {})\n",
+                feature.name, feature.description, feature.missing_scopes.join(", ")
+            ));
+        }
+    }
+    let mut commands: Vec<&str> = status.credentials.iter()
+        .map(|c| c.reauth_command.as_str())
+        .filter(|c| !c.is_empty())
+        .collect();
+    commands.dedup();
+    if !commands.is_empty() {
+        out.push_str("\nTo grant the missing permissions, log in again with:\n");
+        for command in commands {
+            out.push_str(&format!("  {}\n", command));
+        }
+    }
+    out
+}
+
 async fn check_has_broadcaster(client: &GrpcClient, platform: Platform) -> Result<bool, String> {
     let request = ListCredentialsRequest {
         platforms: vec![platform as i32],
@@ -295,6 +409,7 @@ async fn find_or_create_user(client: &GrpcClient, username: &str) -> Result<Stri
             page_size: 10,
             page_token: String::new(),
         }),
+        read_mask: None,
     };
     
     let search_response = user_client
@@ -335,13 +450,19 @@ async fn oauth_add_flow(
     platform: Platform,
     user_id: String,
     is_bot: bool,
+    device: bool,
 ) -> Result<String, String> {
+    if device {
+        return device_add_flow(client, platform, user_id, is_bot).await;
+    }
+    
     // Begin auth flow
     let request = BeginAuthFlowRequest {
         platform: platform as i32,
         is_bot,
         redirect_uri: "http://127.0.0.1:9876".to_string(),
         requested_scopes: vec![],
+        device_code: false,
     };
     
     let mut cred_client = client.credential.clone();
@@ -393,10 +514,10 @@ async fn oauth_add_flow(
     // Complete auth flow
     let complete_request = CompleteAuthFlowRequest {
         platform: platform as i32,
-        state: state,
+        state,
         auth_data: Some(AuthData::OauthCode(complete_auth_flow_request::OauthCode {
             code: callback_result.code,
-            user_id: user_id,
+            user_id,
             code_verifier: String::new(),
         })),
     };
@@ -412,10 +533,94 @@ async fn oauth_add_flow(
     Ok(credential.credential_id)
 }
 
+/// Device-code login: show a short code + URL, then poll until the user approves it.
+/// Needs no browser or callback port on this machine.
+async fn device_add_flow(
+    client: &GrpcClient,
+    platform: Platform,
+    user_id: String,
+    is_bot: bool,
+) -> Result<String, String> {
+    let request = BeginAuthFlowRequest {
+        platform: platform as i32,
+        is_bot,
+        redirect_uri: String::new(),
+        requested_scopes: vec![],
+        device_code: true,
+    };
+    
+    let mut cred_client = client.credential.clone();
+    let begin_response = cred_client
+        .begin_auth_flow(request)
+        .await
+        .map_err(|e| e.message().to_string())?
+        .into_inner();
+        
+    let user_code = begin_response.metadata.get("user_code").cloned().unwrap_or_default();
+    let interval = begin_response.metadata.get("interval")
+        .and_then(|i| i.parse::<u64>().ok())
+        .unwrap_or(5);
+    
+    println!("\nOn any device, open:\n  {}", begin_response.auth_url);
+    println!("and enter the code:  {}", user_code);
+    if is_bot {
+        println!("(Bot account) Make sure you're signed in as the bot there, not your main account.");
+    }
+    println!("Waiting for approval...");
+    
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        
+        let poll_request = CompleteAuthFlowRequest {
+            platform: platform as i32,
+            state: begin_response.state.clone(),
+            auth_data: Some(AuthData::DeviceCode(complete_auth_flow_request::DeviceCode {
+                user_id: user_id.clone(),
+            })),
+        };
+        let poll_response = cred_client
+            .complete_auth_flow(poll_request)
+            .await
+            .map_err(|e| e.message().to_string())?
+            .into_inner();
+            
+        if poll_response.pending {
+            continue;
+        }
+        let credential = poll_response.credential
+            .ok_or("No credential returned from device login")?;
+        println!("Approved as '{}'.", credential.user_name);
+        return Ok(credential.credential_id);
+    }
+}
+
+/// Logs a VRChat account back in (`vrchat login`) under its existing user,
+/// keeping the credential's role flags.
+pub(crate) async fn vrchat_relogin(
+    client: &GrpcClient,
+    credential: &maowbot_proto::maowbot::common::PlatformCredential,
+) -> Result<String, String> {
+    let credential_id = vrchat_add_flow(
+        client,
+        Platform::Vrchat,
+        credential.user_id.clone(),
+        Some(&credential.user_name),
+    ).await?;
+    update_credential_flags(
+        client,
+        &credential_id,
+        credential.is_bot,
+        credential.is_broadcaster,
+        credential.is_teammate,
+    ).await?;
+    Ok(credential_id)
+}
+
 async fn vrchat_add_flow(
     client: &GrpcClient,
     platform: Platform,
     user_id: String,
+    known_username: Option<&str>,
 ) -> Result<String, String> {
     // Begin auth flow to check if it's multi-key
     let request = BeginAuthFlowRequest {
@@ -423,6 +628,7 @@ async fn vrchat_add_flow(
         is_bot: false,
         redirect_uri: "".to_string(),
         requested_scopes: vec![],
+        device_code: false,
     };
     
     let mut cred_client = client.credential.clone();
@@ -438,10 +644,18 @@ async fn vrchat_add_flow(
     }
     
     // Prompt for username and password
-    print!("Enter your VRChat username: ");
-    let _ = stdout().flush();
     let mut username_line = String::new();
-    stdin().read_line(&mut username_line).map_err(|e| e.to_string())?;
+    match known_username {
+        Some(name) => {
+            println!("VRChat username: {}", name);
+            username_line.push_str(name);
+        }
+        None => {
+            print!("Enter your VRChat username: ");
+            let _ = stdout().flush();
+            stdin().read_line(&mut username_line).map_err(|e| e.to_string())?;
+        }
+    }
     
     print!("Enter your VRChat password: ");
     let _ = stdout().flush();
@@ -486,7 +700,7 @@ async fn vrchat_add_flow(
                 // Complete with 2FA
                 let twofa_request = CompleteAuthFlowRequest {
                     platform: platform as i32,
-                    state: state,
+                    state,
                     auth_data: Some(AuthData::TwoFactorCode(complete_auth_flow_request::TwoFactorCode {
                         code,
                         user_id,
@@ -521,6 +735,7 @@ async fn discord_bot_add_flow(
         is_bot: true,
         redirect_uri: "".to_string(),
         requested_scopes: vec![],
+        device_code: false,
     };
     
     let mut cred_client = client.credential.clone();
@@ -565,7 +780,6 @@ async fn prompt_discord_bot_token_and_fetch() -> Result<HashMap<String, String>,
     struct DiscordMe {
         id: String,
         username: String,
-        discriminator: String,
     }
 
     println!("\nDiscord flow => we'll ask for your Bot token, fetch /users/@me, and then your Application ID.\n");
@@ -813,7 +1027,7 @@ async fn reuse_twitch_helix_for_eventsub(
         seconds: Utc::now().timestamp(),
         nanos: 0,
     });
-    eventsub_cred.last_refreshed = eventsub_cred.created_at.clone();
+    eventsub_cred.last_refreshed = eventsub_cred.created_at;
     
     // Store the new credential
     let store_request = StoreCredentialRequest {
@@ -837,6 +1051,7 @@ fn parse_platform(platform_str: &str) -> Result<Platform, String> {
         "discord" => Ok(Platform::Discord),
         "vrchat" => Ok(Platform::Vrchat),
         "vrchat-pipeline" | "vrchatpipeline" => Ok(Platform::VrchatPipeline),
+        "kick" => Ok(Platform::Kick),
         _ => Err(format!("Unknown platform '{}'", platform_str)),
     }
 }
\ No newline at end of file
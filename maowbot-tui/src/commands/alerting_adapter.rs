@@ -0,0 +1,227 @@
+// Ops alerting command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::alerting::AlertingCommands};
+use maowbot_proto::maowbot::services::{AddAlertRuleRequest, AlertKinds, AlertRule, RaisedAlert, UpdateAlertRuleRequest};
+
+const DEFAULT_DEDUPE_SECS: i32 = 900;
+
+pub async fn handle_alerting_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "rules" | "list" => match AlertingCommands::list_rules(client).await {
+            Ok(rules) if rules.is_empty() => "No alert rules; alerts are only logged.".to_string(),
+            Ok(rules) => {
+                let mut out = String::new();
+                for rule in &rules {
+                    out.push_str(&format_rule(rule));
+                    out.push('\n');
+                }
+                out
+            }
+            Err(e) => format!("Error listing alert rules => {}", e),
+        },
+
+        "add" => add(&args[1..], client).await,
+        "set" | "edit" => edit(&args[1..], client).await,
+
+        "remove" | "delete" => {
+            let Some(name) = args.get(1) else {
+                return "Usage: alerting remove <name>".to_string();
+            };
+            match AlertingCommands::delete_rule(client, name).await {
+                Ok(()) => format!("Removed alert rule '{}'.", name),
+                Err(e) => format!("Error removing alert rule => {}", e),
+            }
+        }
+
+        "enable" | "disable" => {
+            let Some(name) = args.get(1) else {
+                return format!("Usage: alerting {} <name>", args[0].to_lowercase());
+            };
+            let enabled = args[0].eq_ignore_ascii_case("enable");
+            let request = UpdateAlertRuleRequest { name: name.to_string(), enabled: Some(enabled), ..Default::default() };
+            match AlertingCommands::update_rule(client, request).await {
+                Ok(r) => format!("Alert rule '{}' is now {}.", r.name, if r.enabled { "enabled" } else { "disabled" }),
+                Err(e) => format!("Error updating alert rule => {}", e),
+            }
+        }
+
+        "recent" | "history" => {
+            let limit = match args.get(1).map(|n| n.parse::<u32>()) {
+                None => 0,
+                Some(Ok(n)) => n,
+                Some(Err(_)) => return "Usage: alerting recent [count]".to_string(),
+            };
+            match AlertingCommands::recent_alerts(client, limit).await {
+                Ok(alerts) if alerts.is_empty() => "No alerts since the server started.".to_string(),
+                Ok(alerts) => alerts.iter().map(format_alert).collect::<Vec<_>>().join("\n"),
+                Err(e) => format!("Error listing alerts => {}", e),
+            }
+        }
+
+        "test" => {
+            let kind = args.get(1).copied().unwrap_or_default();
+            let severity = args.get(2).copied().unwrap_or_default();
+            match AlertingCommands::test_alert(client, kind, severity).await {
+                Ok(alert) if alert.deliveries.is_empty() => {
+                    format!("{}\n  No rule matches this kind and severity.", format_alert(&alert))
+                }
+                Ok(alert) => format_alert(&alert),
+                Err(e) => format!("Error raising test alert => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  alerting rules\n");
+    out.push_str("  alerting add <name> <dm|chat|desktop> [--to TARGET] [--kinds K,K] [--min SEVERITY]\n");
+    out.push_str("               [--dedupe SECS] [--quiet HH:MM-HH:MM]\n");
+    out.push_str("  alerting set <name> [--route R] [--to TARGET|none] [--kinds K,K|all] [--min SEVERITY]\n");
+    out.push_str("               [--dedupe SECS] [--quiet HH:MM-HH:MM|none]\n");
+    out.push_str("  alerting remove <name>\n");
+    out.push_str("  alerting enable <name>\n");
+    out.push_str("  alerting disable <name>\n");
+    out.push_str("  alerting recent [count]\n");
+    out.push_str("  alerting test [platform|credential|pipeline|disk] [info|warning|critical]\n");
+    out
+}
+
+/// Options shared by "add" and "set".
+#[derive(Default)]
+struct RuleOptions {
+    route: Option<String>,
+    target: Option<String>,
+    kinds: Option<Vec<String>>,
+    min_severity: Option<String>,
+    dedupe_secs: Option<i32>,
+    quiet_hours: Option<String>,
+}
+
+/// "none" clears a target or quiet hours.
+fn clearable(value: &str) -> String {
+    if value.eq_ignore_ascii_case("none") { String::new() } else { value.to_string() }
+}
+
+fn parse_options(args: &[&str]) -> Result<RuleOptions, String> {
+    let mut options = RuleOptions::default();
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i];
+        let Some(value) = args.get(i + 1).copied() else {
+            return Err(format!("Missing value for {}", flag));
+        };
+        match flag {
+            "--route" => options.route = Some(value.to_lowercase()),
+            "--to" | "--target" => options.target = Some(clearable(value)),
+            "--kinds" | "--kind" => {
+                let kinds = if value.eq_ignore_ascii_case("all") {
+                    Vec::new()
+                } else {
+                    value.split(',').filter(|k| !k.is_empty()).map(str::to_string).collect()
+                };
+                options.kinds = Some(kinds);
+            }
+            "--min" | "--severity" => options.min_severity = Some(value.to_lowercase()),
+            "--dedupe" => {
+                let secs = value.parse::<i32>().ok().filter(|n| *n >= 0)
+                    .ok_or_else(|| format!("Invalid number '{}' for --dedupe", value))?;
+                options.dedupe_secs = Some(secs);
+            }
+            "--quiet" => options.quiet_hours = Some(clearable(value)),
+            _ => return Err(format!("Unknown option '{}'\n{}", flag, usage())),
+        }
+        i += 2;
+    }
+    Ok(options)
+}
+
+async fn add(args: &[&str], client: &GrpcClient) -> String {
+    if args.len() < 2 {
+        return "Usage: alerting add <name> <dm|chat|desktop> [options]".to_string();
+    }
+    let options = match parse_options(&args[2..]) {
+        Ok(options) => options,
+        Err(e) => return e,
+    };
+    let request = AddAlertRuleRequest {
+        name: args[0].to_string(),
+        kinds: options.kinds.unwrap_or_default(),
+        min_severity: options.min_severity.unwrap_or_default(),
+        route: args[1].to_string(),
+        target: options.target.unwrap_or_default(),
+        dedupe_secs: options.dedupe_secs.unwrap_or(DEFAULT_DEDUPE_SECS),
+        quiet_hours: options.quiet_hours.unwrap_or_default(),
+    };
+    match AlertingCommands::add_rule(client, request).await {
+        Ok(rule) => format!("Added {}", format_rule(&rule)),
+        Err(e) => format!("Error adding alert rule => {}", e),
+    }
+}
+
+async fn edit(args: &[&str], client: &GrpcClient) -> String {
+    let Some(name) = args.first() else {
+        return "Usage: alerting set <name> [options]".to_string();
+    };
+    let options = match parse_options(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => return e,
+    };
+    let request = UpdateAlertRuleRequest {
+        name: name.to_string(),
+        kinds: options.kinds.map(|kinds| AlertKinds { kinds }),
+        min_severity: options.min_severity,
+        route: options.route,
+        target: options.target,
+        dedupe_secs: options.dedupe_secs,
+        quiet_hours: options.quiet_hours,
+        enabled: None,
+    };
+    match AlertingCommands::update_rule(client, request).await {
+        Ok(rule) => format!("Updated {}", format_rule(&rule)),
+        Err(e) => format!("Error updating alert rule => {}", e),
+    }
+}
+
+fn format_rule(r: &AlertRule) -> String {
+    let destination = match (r.route.as_str(), r.target.is_empty()) {
+        ("chat", true) => "chat (broadcaster's channel)".to_string(),
+        (_, true) => r.route.clone(),
+        (route, false) => format!("{} {}", route, r.target),
+    };
+    format!(
+        "{}{} -> {}: {} at {}+, dedupe {}s{}",
+        r.name,
+        if r.enabled { "" } else { " (disabled)" },
+        destination,
+        if r.kinds.is_empty() { "every kind".to_string() } else { r.kinds.join(",") },
+        r.min_severity,
+        r.dedupe_secs,
+        if r.quiet_hours.is_empty() { String::new() } else { format!(", quiet {}", r.quiet_hours) }
+    )
+}
+
+fn format_alert(a: &RaisedAlert) -> String {
+    let time = a.raised_at.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let mut out = format!("{} [{}] {} {}", time, a.severity, a.kind, a.title);
+    if !a.message.is_empty() {
+        out.push_str(&format!(": {}", a.message));
+    }
+    for delivery in &a.deliveries {
+        if delivery.error.is_empty() {
+            out.push_str(&format!("\n  {} -> {}", delivery.rule, delivery.outcome));
+        } else {
+            out.push_str(&format!("\n  {} -> {} ({})", delivery.rule, delivery.outcome, delivery.error));
+        }
+    }
+    out
+}
@@ -0,0 +1,82 @@
+// Alias command adapter for TUI - manages user-defined command aliases
+use std::sync::Arc;
+use crate::aliases::parse_definition;
+use crate::tui_module_simple::SimpleTuiModule;
+use super::dispatch_grpc::BUILTIN_COMMANDS;
+
+pub fn handle_alias_command(args: &[&str], tui_module: &Arc<SimpleTuiModule>) -> String {
+    let usage = "Usage: alias <add|remove|list> ...  (or: alias <name> = <command>[; <command>...])";
+
+    match args.first() {
+        None | Some(&"list") => list_aliases(tui_module),
+
+        Some(&"add") => {
+            if args.len() < 3 {
+                return "Usage: alias add <name> [=] <command>[; <command>...]".to_string();
+            }
+            let expansion = args[2..].join(" ");
+            let expansion = expansion.strip_prefix('=').unwrap_or(&expansion).trim();
+            match parse_definition(&format!("{} = {}", args[1], expansion)) {
+                Ok((name, expansion)) => add_alias(tui_module, &name, &expansion),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+
+        Some(&"remove") | Some(&"rm") | Some(&"delete") => {
+            let Some(name) = args.get(1) else {
+                return "Usage: alias remove <name>".to_string();
+            };
+            let mut aliases = tui_module.aliases.lock().unwrap();
+            match aliases.remove(name) {
+                Ok(true) => format!("Removed alias '{}'.", name),
+                Ok(false) => format!("Error: No alias named '{}'.", name),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+
+        Some(&"show") => {
+            let Some(name) = args.get(1) else {
+                return "Usage: alias show <name>".to_string();
+            };
+            match tui_module.aliases.lock().unwrap().get(name) {
+                Some(expansion) => format!("{} = {}", name, expansion),
+                None => format!("Error: No alias named '{}'.", name),
+            }
+        }
+
+        // Shorthand: alias brb = connection stop twitch-irc mybot; osc set AFK true
+        Some(_) if args.contains(&"=") || args[0].contains('=') => {
+            match parse_definition(&args.join(" ")) {
+                Ok((name, expansion)) => add_alias(tui_module, &name, &expansion),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+
+        Some(_) => usage.to_string(),
+    }
+}
+
+fn add_alias(tui_module: &Arc<SimpleTuiModule>, name: &str, expansion: &str) -> String {
+    if BUILTIN_COMMANDS.contains(&name) {
+        return format!("Error: '{}' is a built-in command and cannot be used as an alias name.", name);
+    }
+    let mut aliases = tui_module.aliases.lock().unwrap();
+    match aliases.set(name, expansion) {
+        Ok(Some(previous)) => format!("Updated alias '{}' (was: {}).", name, previous),
+        Ok(None) => format!("Added alias '{}' = {}", name, expansion),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+fn list_aliases(tui_module: &Arc<SimpleTuiModule>) -> String {
+    let aliases = tui_module.aliases.lock().unwrap();
+    if aliases.is_empty() {
+        return "No aliases defined. Add one with: alias <name> = <command>[; <command>...]".to_string();
+    }
+    let width = aliases.list().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut output = String::from("Aliases:\n");
+    for (name, expansion) in aliases.list() {
+        output.push_str(&format!("  {:width$} = {}\n", name, expansion, width = width));
+    }
+    output
+}
@@ -0,0 +1,117 @@
+// Audit log command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::audit::{AuditCommands, AuditQuery}};
+
+/// Longest before/after value shown per line.
+const VALUE_WIDTH: usize = 60;
+
+pub async fn handle_audit_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "l" | "list" => {
+            let query = match parse_query(&args[1..]) {
+                Ok(q) => q,
+                Err(e) => return e,
+            };
+            match AuditCommands::list(client, &query).await {
+                Ok(entries) if entries.is_empty() => "No matching audit entries.".to_string(),
+                Ok(entries) => {
+                    let mut out = String::new();
+                    for e in &entries {
+                        let when = e.created_at
+                            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default();
+                        let who = if e.client_name.is_empty() { "<anonymous>" } else { e.client_name.as_str() };
+                        let result = if !e.allowed {
+                            "DENIED"
+                        } else if e.outcome.is_empty() {
+                            "-"
+                        } else {
+                            e.outcome.as_str()
+                        };
+                        let method = e.method.rsplit('/').next().unwrap_or(&e.method);
+                        out.push_str(&format!("{} {:16} {:16} {}", when, who, method, result));
+                        if !e.workspace.is_empty() {
+                            out.push_str(&format!(" [{}]", e.workspace));
+                        }
+                        if !e.reason.is_empty() {
+                            out.push_str(&format!(" ({})", e.reason));
+                        }
+                        out.push('\n');
+                        if !e.target.is_empty() {
+                            out.push_str(&format!(
+                                "    {}: {} -> {}\n",
+                                e.target, shown(&e.before_value), shown(&e.after_value)
+                            ));
+                        }
+                    }
+                    out
+                }
+                Err(e) => format!("Error reading audit log => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn parse_query(args: &[&str]) -> Result<AuditQuery, String> {
+    let mut query = AuditQuery { limit: 50, changes_only: true, ..Default::default() };
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1).map(|v| v.to_string());
+        let missing = || format!("{} needs a value.\n{}", args[i], usage());
+        match args[i] {
+            "--all" => {
+                query.changes_only = false;
+                i += 1;
+                continue;
+            }
+            "--limit" => {
+                query.limit = value.and_then(|n| n.parse().ok()).filter(|n| *n > 0)
+                    .ok_or_else(|| "--limit must be a positive number.".to_string())?;
+            }
+            "--method" => query.method = value.ok_or_else(missing)?,
+            "--by" => query.client_name = value.ok_or_else(missing)?,
+            "--target" => query.target = value.ok_or_else(missing)?,
+            "--since" => {
+                let window = value.ok_or_else(missing)?;
+                query.since = Some(chrono::Utc::now() - parse_age(&window)?);
+            }
+            other => return Err(format!("Unknown option '{}'.\n{}", other, usage())),
+        }
+        i += 2;
+    }
+    Ok(query)
+}
+
+/// "30m", "12h" or "7d".
+fn parse_age(arg: &str) -> Result<chrono::Duration, String> {
+    let invalid = || format!("Invalid age '{}'. Use e.g. 30m, 12h or 7d.", arg);
+    let split = arg.len().checked_sub(1).ok_or_else(invalid)?;
+    let (n, unit) = arg.split_at(split);
+    let n: i64 = n.parse().ok().filter(|n| *n > 0).ok_or_else(invalid)?;
+    match unit {
+        "m" => Ok(chrono::Duration::minutes(n)),
+        "h" => Ok(chrono::Duration::hours(n)),
+        "d" => Ok(chrono::Duration::days(n)),
+        _ => Err(invalid()),
+    }
+}
+
+fn shown(value: &str) -> String {
+    if value.is_empty() {
+        return "(none)".to_string();
+    }
+    if value.chars().count() <= VALUE_WIDTH {
+        value.to_string()
+    } else {
+        format!("{}...", value.chars().take(VALUE_WIDTH - 3).collect::<String>())
+    }
+}
+
+fn usage() -> String {
+    "Usage:\n  audit list [--limit N] [--since 30m|12h|7d] [--by <client>] [--method <name>] [--target <text>] [--all]\n".to_string()
+}
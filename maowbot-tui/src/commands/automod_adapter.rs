@@ -0,0 +1,310 @@
+// Moderation rule command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::moderation_rules::ModerationRuleCommands};
+use maowbot_proto::maowbot::services::{AddRuleRequest, ChatRule, UpdateRuleRequest};
+
+pub async fn handle_automod_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "list" => match ModerationRuleCommands::list_rules(client).await {
+            Ok(rules) if rules.is_empty() => "No moderation rules yet.".to_string(),
+            Ok(rules) => {
+                let mut out = String::new();
+                for rule in &rules {
+                    out.push_str(&format_rule(rule));
+                    out.push('\n');
+                }
+                out
+            }
+            Err(e) => format!("Error listing rules => {}", e),
+        },
+
+        "add" => add(&args[1..], client).await,
+        "edit" => edit(&args[1..], client).await,
+
+        "remove" | "delete" => {
+            let Some(name) = args.get(1) else {
+                return "Usage: automod remove <name>".to_string();
+            };
+            match ModerationRuleCommands::delete_rule(client, name).await {
+                Ok(()) => format!("Removed rule '{}'.", name),
+                Err(e) => format!("Error removing rule => {}", e),
+            }
+        }
+
+        "enable" | "disable" => {
+            let Some(name) = args.get(1) else {
+                return format!("Usage: automod {} <name>", args[0].to_lowercase());
+            };
+            let enabled = args[0].eq_ignore_ascii_case("enable");
+            let request = UpdateRuleRequest { name: name.to_string(), enabled: Some(enabled), ..Default::default() };
+            match ModerationRuleCommands::update_rule(client, request).await {
+                Ok(rule) => format!("Rule '{}' is now {}.", rule.name, if rule.enabled { "enabled" } else { "disabled" }),
+                Err(e) => format!("Error updating rule => {}", e),
+            }
+        }
+
+        "reset" => {
+            let Some(name) = args.get(1) else {
+                return "Usage: automod reset <name>".to_string();
+            };
+            match ModerationRuleCommands::reset_hits(client, name).await {
+                Ok(rule) => format!("Reset the hit counter of '{}'.", rule.name),
+                Err(e) => format!("Error resetting rule => {}", e),
+            }
+        }
+
+        "test" => test(&args[1..], client).await,
+        "offenses" => offenses(&args[1..], client).await,
+        "pardon" => pardon(&args[1..], client).await,
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  automod list\n");
+    out.push_str("  automod add <name> <link|phrase|regex> [pattern...] [--severity delete|timeout|ban]\n");
+    out.push_str("              [--timeout SECS] [--permit subs,vips,mods|none] [--category C]\n");
+    out.push_str("  automod edit <name> [pattern...] [--severity S] [--timeout SECS] [--permit LIST]\n");
+    out.push_str("              [--category C|none]  # a category escalates: warn, timeout, ban\n");
+    out.push_str("  automod remove <name>\n");
+    out.push_str("  automod enable <name>\n");
+    out.push_str("  automod disable <name>\n");
+    out.push_str("  automod reset <name>              # zero the hit counter\n");
+    out.push_str("  automod test [--as sub|vip|mod] <message...>\n");
+    out.push_str("  automod offenses <user>           # where a user is on the escalation ladder\n");
+    out.push_str("  automod pardon <user> [category] [--lift]\n");
+    out
+}
+
+/// Options shared by "add" and "edit".
+#[derive(Default)]
+struct RuleOptions {
+    pattern: Vec<String>,
+    severity: Option<String>,
+    timeout_secs: Option<i32>,
+    /// (subs, vips, mods)
+    permits: Option<(bool, bool, bool)>,
+    /// "none" clears it
+    category: Option<String>,
+}
+
+fn parse_options(args: &[&str]) -> Result<RuleOptions, String> {
+    let mut options = RuleOptions::default();
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i];
+        if !flag.starts_with("--") {
+            options.pattern.push(flag.to_string());
+            i += 1;
+            continue;
+        }
+        let Some(value) = args.get(i + 1).copied() else {
+            return Err(format!("Missing value for {}", flag));
+        };
+        match flag {
+            "--severity" => options.severity = Some(value.to_lowercase()),
+            "--timeout" => {
+                let secs = value.parse::<i32>().ok().filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid number '{}' for --timeout", value))?;
+                options.timeout_secs = Some(secs);
+            }
+            "--permit" => {
+                let mut permits = (false, false, false);
+                for role in value.split(',').map(|r| r.trim().to_lowercase()) {
+                    match role.as_str() {
+                        "subs" | "sub" => permits.0 = true,
+                        "vips" | "vip" => permits.1 = true,
+                        "mods" | "mod" => permits.2 = true,
+                        "none" | "" => {}
+                        other => return Err(format!("Unknown role '{}' for --permit", other)),
+                    }
+                }
+                options.permits = Some(permits);
+            }
+            "--category" => {
+                let category = value.to_lowercase();
+                options.category = Some(if category == "none" { String::new() } else { category });
+            }
+            _ => return Err(format!("Unknown option '{}'\n{}", flag, usage())),
+        }
+        i += 2;
+    }
+    Ok(options)
+}
+
+async fn add(args: &[&str], client: &GrpcClient) -> String {
+    if args.len() < 2 {
+        return "Usage: automod add <name> <link|phrase|regex> [pattern...] [options]".to_string();
+    }
+    let options = match parse_options(&args[2..]) {
+        Ok(options) => options,
+        Err(e) => return e,
+    };
+    // Mods are permitted unless --permit says otherwise
+    let (permit_subs, permit_vips, permit_mods) = options.permits.unwrap_or((false, false, true));
+    let request = AddRuleRequest {
+        name: args[0].to_string(),
+        kind: args[1].to_string(),
+        pattern: options.pattern.join(" "),
+        severity: options.severity.unwrap_or_default(),
+        timeout_secs: options.timeout_secs.unwrap_or_default(),
+        permit_subs,
+        permit_vips,
+        permit_mods,
+        category: options.category.unwrap_or_default(),
+    };
+    match ModerationRuleCommands::add_rule(client, request).await {
+        Ok(rule) => format!("Added {}", format_rule(&rule)),
+        Err(e) => format!("Error adding rule => {}", e),
+    }
+}
+
+async fn edit(args: &[&str], client: &GrpcClient) -> String {
+    let Some(name) = args.first() else {
+        return "Usage: automod edit <name> [pattern...] [options]".to_string();
+    };
+    let options = match parse_options(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => return e,
+    };
+    let request = UpdateRuleRequest {
+        name: name.to_string(),
+        pattern: (!options.pattern.is_empty()).then(|| options.pattern.join(" ")),
+        severity: options.severity,
+        timeout_secs: options.timeout_secs,
+        permit_subs: options.permits.map(|p| p.0),
+        permit_vips: options.permits.map(|p| p.1),
+        permit_mods: options.permits.map(|p| p.2),
+        category: options.category,
+        enabled: None,
+    };
+    match ModerationRuleCommands::update_rule(client, request).await {
+        Ok(rule) => format!("Updated {}", format_rule(&rule)),
+        Err(e) => format!("Error updating rule => {}", e),
+    }
+}
+
+async fn test(args: &[&str], client: &GrpcClient) -> String {
+    let mut roles = Vec::new();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--as" {
+            let Some(role) = args.get(i + 1) else {
+                return "Missing value for --as".to_string();
+            };
+            roles.push(match role.to_lowercase().as_str() {
+                "sub" | "subs" => "subscriber".to_string(),
+                "mod" | "mods" => "moderator".to_string(),
+                other => other.to_string(),
+            });
+            i += 2;
+        } else {
+            words.push(args[i]);
+            i += 1;
+        }
+    }
+    if words.is_empty() {
+        return "Usage: automod test [--as sub|vip|mod] <message...>".to_string();
+    }
+
+    match ModerationRuleCommands::test_message(client, &words.join(" "), roles).await {
+        Ok(hits) if hits.is_empty() => "No rule matches; the message would be allowed.".to_string(),
+        Ok(hits) => {
+            let mut out = String::new();
+            for (i, hit) in hits.iter().enumerate() {
+                let action = if hit.severity == "timeout" {
+                    format!("timeout {}s", hit.timeout_secs)
+                } else {
+                    hit.severity.clone()
+                };
+                out.push_str(&format!(
+                    "{} {} -> {} (matched \"{}\")\n",
+                    if i == 0 { "*" } else { " " }, hit.name, action, hit.matched
+                ));
+            }
+            out.push_str("* is the action that would be taken.\n");
+            out
+        }
+        Err(e) => format!("Error testing message => {}", e),
+    }
+}
+
+async fn offenses(args: &[&str], client: &GrpcClient) -> String {
+    let Some(user) = args.first() else {
+        return "Usage: automod offenses <user>".to_string();
+    };
+    match ModerationRuleCommands::list_offenses(client, user).await {
+        Ok(offenses) if offenses.is_empty() => format!("{} has no offenses on the ladder.", user),
+        Ok(offenses) => {
+            let mut out = String::new();
+            for o in &offenses {
+                let last = o.last_offense_at.as_ref()
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "{}: {} offense(s), last {}, next -> {}\n",
+                    o.category, o.count, last, o.next_step
+                ));
+            }
+            out
+        }
+        Err(e) => format!("Error listing offenses => {}", e),
+    }
+}
+
+async fn pardon(args: &[&str], client: &GrpcClient) -> String {
+    let lift = args.contains(&"--lift");
+    let rest: Vec<&str> = args.iter().copied().filter(|a| *a != "--lift").collect();
+    let Some(user) = rest.first() else {
+        return "Usage: automod pardon <user> [category] [--lift]".to_string();
+    };
+    let category = rest.get(1).copied().unwrap_or_default();
+    match ModerationRuleCommands::pardon(client, user, category, lift).await {
+        Ok(categories) if categories.is_empty() && !lift => format!("{} had no offenses to pardon.", user),
+        Ok(categories) => format!(
+            "Pardoned {}{}{}.",
+            user,
+            if categories.is_empty() { String::new() } else { format!(" in {}", categories.join(", ")) },
+            if lift { " and lifted the ban/timeout" } else { "" }
+        ),
+        Err(e) => format!("Error pardoning user => {}", e),
+    }
+}
+
+fn format_rule(r: &ChatRule) -> String {
+    let action = if !r.category.is_empty() {
+        format!("escalates ({})", r.category)
+    } else if r.severity == "timeout" {
+        format!("timeout {}s", r.timeout_secs)
+    } else {
+        r.severity.clone()
+    };
+    let permits: Vec<&str> = [(r.permit_subs, "subs"), (r.permit_vips, "vips"), (r.permit_mods, "mods")]
+        .iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, role)| *role)
+        .collect();
+    let last_hit = r.last_hit_at.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+        .map(|t| format!(", last {}", t.format("%Y-%m-%d %H:%M")))
+        .unwrap_or_default();
+    format!(
+        "{}{} [{}] {} -> {}, permits {}, {} hit(s){}",
+        r.name,
+        if r.enabled { "" } else { " (disabled)" },
+        r.kind,
+        if r.kind == "link" { format!("allow: {}", r.pattern) } else { r.pattern.clone() },
+        action,
+        if permits.is_empty() { "nobody".to_string() } else { permits.join(",") },
+        r.hit_count,
+        last_hit
+    )
+}
@@ -0,0 +1,299 @@
+// Chat archive command adapter for TUI
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, Timelike, Utc};
+use maowbot_common_ui::{GrpcClient, commands::chat_archive::{
+    ActivityBucket, ArchivedMessage, ChatActivity, ChatArchiveCommands, ChatSearchFilter,
+}};
+use super::paging::PageArgs;
+
+const DEFAULT_SEARCH_LIMIT: usize = 25;
+const DEFAULT_PEAKS: i32 = 5;
+/// Heatmap cells from empty to busiest.
+const HEAT: [char; 5] = [' ', '.', ':', '*', '#'];
+
+pub async fn handle_chatlog_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "search" | "s" => search(&args[1..], client).await,
+
+        "lastseen" => {
+            if args.len() < 2 {
+                return "Usage: chatlog lastseen <user> [channel]".to_string();
+            }
+            match ChatArchiveCommands::last_seen(client, args[1], args.get(2).copied()).await {
+                Ok(Some(msg)) => format_message(&msg),
+                Ok(None) => format!("No archived messages from '{}'.", args[1]),
+                Err(e) => format!("Error looking up '{}' => {}", args[1], e),
+            }
+        }
+
+        "retention" => retention(&args[1..], client).await,
+
+        "activity" => activity(&args[1..], client).await,
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  chatlog search [text] [--user U] [--channel C] [--platform P] [--since T] [--until T] [--limit N] [--offset N]\n");
+    out.push_str("  chatlog lastseen <user> [channel]\n");
+    out.push_str("  chatlog retention                                # list per-channel retention\n");
+    out.push_str("  chatlog retention set <platform> <channel> <days>\n");
+    out.push_str("  chatlog retention clear <platform> <channel>     # back to the default\n");
+    out.push_str("  chatlog activity [--platform P] [--channel C] [--since T] [--until T] [--peaks N]\n");
+    out
+}
+
+/// "7d", "12h", "30m" ago, or a date (YYYY-MM-DD, UTC midnight).
+pub(super) fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    let err = || format!("Invalid time '{}': use e.g. 7d, 12h, 30m or YYYY-MM-DD", value);
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc()).ok_or_else(err);
+    }
+    let split = value.char_indices().last().map(|(i, _)| i).unwrap_or(0);
+    let (num, unit) = value.split_at(split);
+    let n: i64 = num.parse().map_err(|_| err())?;
+    let ago = match unit {
+        "d" => Duration::days(n),
+        "h" => Duration::hours(n),
+        "m" => Duration::minutes(n),
+        _ => return Err(err()),
+    };
+    Ok(Utc::now() - ago)
+}
+
+fn format_message(msg: &ArchivedMessage) -> String {
+    let when = msg.timestamp
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let who = if msg.username.is_empty() { msg.user_id.as_str() } else { msg.username.as_str() };
+    format!("{} [{} {}] {}: {}", when, msg.platform, msg.channel, who, msg.text)
+}
+
+async fn search(args: &[&str], client: &GrpcClient) -> String {
+    let (page, rest) = match PageArgs::extract(args) {
+        Ok(v) => v,
+        Err(e) => return format!("Error: {}", e),
+    };
+
+    let mut filter = ChatSearchFilter {
+        limit: page.limit.unwrap_or(DEFAULT_SEARCH_LIMIT) as i32,
+        offset: page.offset as i32,
+        ..Default::default()
+    };
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < rest.len() {
+        let flag = rest[i];
+        if !flag.starts_with("--") {
+            words.push(flag);
+            i += 1;
+            continue;
+        }
+        let Some(value) = rest.get(i + 1).copied() else {
+            return format!("Missing value for {}", flag);
+        };
+        match flag {
+            "--user" => filter.user = value.to_string(),
+            "--channel" => filter.channel = value.to_string(),
+            "--platform" => filter.platform = value.to_string(),
+            "--since" => match parse_time(value) {
+                Ok(t) => filter.since = Some(t),
+                Err(e) => return e,
+            },
+            "--until" => match parse_time(value) {
+                Ok(t) => filter.until = Some(t),
+                Err(e) => return e,
+            },
+            _ => return format!("Unknown option '{}'\n{}", flag, usage()),
+        }
+        i += 2;
+    }
+    filter.text = words.join(" ");
+
+    let offset = page.offset;
+    match ChatArchiveCommands::search_messages(client, filter).await {
+        Ok(result) if result.messages.is_empty() => {
+            if result.total_count > 0 {
+                format!("(no messages at offset {}; {} total)", offset, result.total_count)
+            } else {
+                "No matching messages.".to_string()
+            }
+        }
+        Ok(result) => {
+            let mut out = String::new();
+            for msg in &result.messages {
+                out.push_str(&format_message(msg));
+                out.push('\n');
+            }
+            let shown = result.messages.len();
+            let total = result.total_count as usize;
+            out.push_str(&format!("(showing {}-{} of {}", offset + 1, offset + shown, total));
+            if offset + shown < total {
+                out.push_str(&format!("; next: --offset {}", offset + shown));
+            }
+            out.push_str(")\n");
+            out
+        }
+        Err(e) => format!("Error searching chat => {}", e),
+    }
+}
+
+async fn retention(args: &[&str], client: &GrpcClient) -> String {
+    match args.first().map(|s| s.to_lowercase()).as_deref() {
+        None | Some("list") => match ChatArchiveCommands::list_retention(client).await {
+            Ok(result) => {
+                let mut out = format!("Default retention: {} days\n", result.default_retention_days);
+                if result.policies.is_empty() {
+                    out.push_str("No per-channel retention set.\n");
+                }
+                for p in &result.policies {
+                    out.push_str(&format!("  {:16} {:24} {} days\n", p.platform, p.channel, p.retention_days));
+                }
+                out
+            }
+            Err(e) => format!("Error listing retention => {}", e),
+        },
+
+        Some("set") => {
+            if args.len() < 4 {
+                return "Usage: chatlog retention set <platform> <channel> <days>".to_string();
+            }
+            let days = match args[3].parse::<i32>() {
+                Ok(d) if d > 0 => d,
+                _ => return format!("Invalid number of days '{}'", args[3]),
+            };
+            match ChatArchiveCommands::set_retention(client, args[1], args[2], days).await {
+                Ok(()) => format!("Messages from {} {} are now kept for {} days.", args[1], args[2], days),
+                Err(e) => format!("Error setting retention => {}", e),
+            }
+        }
+
+        Some("clear") => {
+            if args.len() < 3 {
+                return "Usage: chatlog retention clear <platform> <channel>".to_string();
+            }
+            match ChatArchiveCommands::delete_retention(client, args[1], args[2]).await {
+                Ok(()) => format!("{} {} now uses the default retention.", args[1], args[2]),
+                Err(e) => format!("Error clearing retention => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+async fn activity(args: &[&str], client: &GrpcClient) -> String {
+    let (mut platform, mut channel) = ("", "");
+    let (mut since, mut until) = (None, None);
+    let mut peaks = DEFAULT_PEAKS;
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i];
+        let Some(value) = args.get(i + 1).copied() else {
+            return format!("Missing value for {}", flag);
+        };
+        match flag {
+            "--platform" => platform = value,
+            "--channel" => channel = value,
+            "--since" => match parse_time(value) {
+                Ok(t) => since = Some(t),
+                Err(e) => return e,
+            },
+            "--until" => match parse_time(value) {
+                Ok(t) => until = Some(t),
+                Err(e) => return e,
+            },
+            "--peaks" => match value.parse::<i32>() {
+                Ok(n) if n >= 0 => peaks = n,
+                _ => return format!("Invalid number of peaks '{}'", value),
+            },
+            _ => return format!("Unknown option '{}'\n{}", flag, usage()),
+        }
+        i += 2;
+    }
+
+    match ChatArchiveCommands::activity(client, platform, channel, since, until, peaks).await {
+        Ok(result) if result.buckets.is_empty() => "No chat activity in that range.".to_string(),
+        Ok(result) => format_activity(&result),
+        Err(e) => format!("Error loading chat activity => {}", e),
+    }
+}
+
+/// One row per channel and hour, each cell a 5-minute bucket scaled against the busiest one.
+fn format_activity(activity: &ChatActivity) -> String {
+    let cells_per_hour = (3600 / activity.bucket_seconds.max(1)).max(1) as usize;
+    let max = activity.buckets.iter().map(|b| b.message_count).max().unwrap_or(0).max(1);
+
+    let mut rows: BTreeMap<(String, String, DateTime<Utc>), Vec<i32>> = BTreeMap::new();
+    for b in &activity.buckets {
+        let Some(start) = b.bucket_start else { continue };
+        let Ok(hour) = start.duration_trunc(Duration::hours(1)) else { continue };
+        let cell = (start.minute() as usize * 60 / activity.bucket_seconds.max(1) as usize).min(cells_per_hour - 1);
+        let row = rows.entry((b.platform.clone(), b.channel.clone(), hour))
+            .or_insert_with(|| vec![0; cells_per_hour]);
+        row[cell] += b.message_count;
+    }
+
+    let mut out = format!("Messages per {} minutes (busiest bucket: {}):\n", activity.bucket_seconds / 60, max);
+    for ((platform, channel, hour), cells) in &rows {
+        let line: String = cells.iter().map(|&n| heat(n, max)).collect();
+        let total: i32 = cells.iter().sum();
+        out.push_str(&format!(
+            "  {:12} {:20} {} |{}| {}\n",
+            platform, channel, hour.format("%Y-%m-%d %H:00"), line, total
+        ));
+    }
+    if !activity.peaks.is_empty() {
+        out.push_str("Peaks:\n");
+        for b in &activity.peaks {
+            out.push_str(&format!("  {}\n", format_bucket(b)));
+        }
+    }
+    out
+}
+
+fn heat(count: i32, max: i32) -> char {
+    if count <= 0 {
+        return HEAT[0];
+    }
+    let steps = (HEAT.len() - 1) as i32;
+    let level = ((count * steps + max - 1) / max).clamp(1, steps);
+    HEAT[level as usize]
+}
+
+fn format_bucket(b: &ActivityBucket) -> String {
+    let when = b.bucket_start
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    format!("{} {} {}: {} messages", when, b.platform, b.channel, b.message_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time() {
+        let now = Utc::now();
+        let t = parse_time("7d").unwrap();
+        assert!((now - Duration::days(7) - t).num_seconds().abs() < 5);
+        assert_eq!(parse_time("2024-03-01").unwrap().to_rfc3339(), "2024-03-01T00:00:00+00:00");
+        assert!(parse_time("7w").is_err());
+        assert!(parse_time("").is_err());
+    }
+
+    #[test]
+    fn test_heat_scale() {
+        assert_eq!(heat(0, 40), ' ');
+        assert_eq!(heat(1, 40), '.');
+        assert_eq!(heat(20, 40), ':');
+        assert_eq!(heat(40, 40), '#');
+    }
+}
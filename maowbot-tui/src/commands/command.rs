@@ -28,7 +28,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
             if args.len() == 1 {
                 // No platform specified => gather from all known platforms
                 // You might keep a list of known platforms or have a dedicated "list_all_commands" function
-                let known_platforms = vec!["twitch-irc", "twitch", "vrchat", "discord", "twitch-eventsub"];
+                let known_platforms = vec!["twitch-irc", "twitch", "vrchat", "discord", "twitch-eventsub", "kick"];
                 let mut out = String::new();
                 for plat in &known_platforms {
                     match bot_api.list_commands(plat).await {
@@ -89,7 +89,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
             }
             let command_name = args[1];
             let sec_str = args[2];
-            let platform = args.get(3).map(|s| *s).unwrap_or("twitch-irc");
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
             let secs = match sec_str.parse::<i32>() {
                 Ok(n) => n,
                 Err(_) => return "Cooldown seconds must be an integer.".to_string(),
@@ -107,7 +107,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
             }
             let command_name = args[1];
             let tf_str = args[2].to_lowercase();
-            let platform = args.get(3).map(|s| *s).unwrap_or("twitch-irc");
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
             let tf = match tf_str.as_str() {
                 "true" | "yes" | "1" => true,
                 "false" | "no" | "0" => false,
@@ -126,7 +126,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
             }
             let command_name = args[1];
             let account_arg = args[2];
-            let platform = args.get(3).map(|s| *s).unwrap_or("");
+            let platform = args.get(3).copied().unwrap_or("");
             match set_respond_with(bot_api, command_name, account_arg, platform).await {
                 Ok(_) => format!("Responding credential updated for '{}'.", command_name),
                 Err(e) => format!("Error => {e}"),
@@ -142,7 +142,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
             }
             let command_name = args[1];
             let new_platform = args[2];
-            let old_platform = args.get(3).map(|s| *s).unwrap_or("twitch-irc");
+            let old_platform = args.get(3).copied().unwrap_or("twitch-irc");
             match set_platform(bot_api, old_platform, command_name, new_platform).await {
                 Ok(_) => format!(
                     "Platform changed for command '{}' from '{}' to '{}'.",
@@ -158,7 +158,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
                 return "Usage: command enable <commandName> [platform]".to_string();
             }
             let command_name = args[1];
-            let platform = args.get(2).map(|s| *s).unwrap_or("twitch-irc");
+            let platform = args.get(2).copied().unwrap_or("twitch-irc");
             match set_active(bot_api, platform, command_name, true).await {
                 Ok(_) => format!("Enabled command '{}'.", command_name),
                 Err(e) => format!("Error => {e}"),
@@ -170,7 +170,7 @@ pub async fn handle_command_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
                 return "Usage: command disable <commandName> [platform]".to_string();
             }
             let command_name = args[1];
-            let platform = args.get(2).map(|s| *s).unwrap_or("twitch-irc");
+            let platform = args.get(2).copied().unwrap_or("twitch-irc");
             match set_active(bot_api, platform, command_name, false).await {
                 Ok(_) => format!("Disabled command '{}'.", command_name),
                 Err(e) => format!("Error => {e}"),
@@ -304,11 +304,7 @@ async fn get_command_by_name(
     cmd_name: &str
 ) -> Result<Command, Error> {
     let all = bot_api.list_commands(platform).await?;
-    let lowered = if cmd_name.starts_with('!') {
-        cmd_name[1..].to_lowercase()
-    } else {
-        cmd_name.to_lowercase()
-    };
+    let lowered = cmd_name.strip_prefix('!').unwrap_or(cmd_name).to_lowercase();
 
     let found = all.into_iter().find(|c| {
         let c_lower = c.command_name.to_lowercase();
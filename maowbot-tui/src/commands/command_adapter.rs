@@ -1,82 +1,155 @@
 // Command command adapter for TUI
+use maowbot_common::models::command::{format_role_cooldowns, parse_role_cooldowns};
 use maowbot_common_ui::{GrpcClient, commands::command::CommandCommands};
+use maowbot_proto::maowbot::services::{GetCommandStatsResponse, ImportCommandsResponse};
+use std::collections::HashMap;
 use std::io::{stdin, stdout, Write};
 use uuid::Uuid;
+use super::paging::PageArgs;
 
 pub async fn handle_command_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
-        return "Usage: command <list|setcooldown|setwarnonce|setrespond|setplatform|enable|disable> [args...]".to_string();
+        return "Usage: command <list|stats|import|setresponse|setcooldown|setusercooldown|setrolecooldown|setbypassmods|setprivate|setreply|setwarnonce|setrespond|setplatform|enable|disable> [args...]".to_string();
     }
     
     match args[0].to_lowercase().as_str() {
         "list" => {
+            let (page, args) = match PageArgs::extract(args) {
+                Ok(parsed) => parsed,
+                Err(e) => return format!("Error: {}", e),
+            };
             // If no platform specified, list from all known platforms
-            if args.len() == 1 {
-                let known_platforms = vec!["twitch-irc", "twitch", "vrchat", "discord", "twitch-eventsub"];
-                let mut out = String::new();
-                
-                for plat in &known_platforms {
-                    match CommandCommands::list_commands(client, Some(plat), false, 100).await {
-                        Ok(result) if !result.data.commands.is_empty() => {
-                            out.push_str(&format!("Commands for platform '{}':\n", plat));
-                            for info in result.data.commands {
-                                if let Some(c) = info.command {
-                                    let warnonce = c.metadata.get("cooldown_warnonce").map(|v| v == "true").unwrap_or(false);
-                                    let respond = c.metadata.get("respond_with_credential");
-                                    out.push_str(&format!(
-                                        " - {} (id={}) active={} cd={}s warnonce={} respond={:?}\n",
-                                        c.name,
-                                        c.command_id,
-                                        c.is_active,
-                                        c.cooldown_seconds,
-                                        warnonce,
-                                        respond
-                                    ));
-                                }
-                            }
-                            out.push('\n');
-                        }
-                        _ => { /* skip if empty or error */ }
+            let platforms: Vec<&str> = match args.get(1) {
+                Some(platform) => vec![*platform],
+                None => vec!["twitch-irc", "twitch", "vrchat", "discord", "twitch-eventsub", "kick"],
+            };
+            
+            let mut commands = Vec::new();
+            for plat in &platforms {
+                match CommandCommands::list_all_commands(client, Some(plat), false).await {
+                    Ok(result) => {
+                        commands.extend(result.data.commands.into_iter().filter_map(|info| info.command).map(|c| (*plat, c)));
                     }
+                    // A single named platform reports its error; the all-platforms view skips failures
+                    Err(e) if platforms.len() == 1 => return format!("Error listing commands: {}", e),
+                    Err(_) => {}
                 }
-                
-                if out.is_empty() {
-                    "No commands found on any platform.".to_string()
-                } else {
-                    out
-                }
-            } else {
-                // Platform specified
-                let platform = args[1];
-                match CommandCommands::list_commands(client, Some(platform), false, 100).await {
-                    Ok(result) => {
-                        if result.data.commands.is_empty() {
-                            format!("No commands found for platform '{}'.", platform)
-                        } else {
-                            let mut out = format!("Commands for platform '{}':\n", platform);
-                            for info in result.data.commands {
-                                if let Some(c) = info.command {
-                                    let warnonce = c.metadata.get("cooldown_warnonce").map(|v| v == "true").unwrap_or(false);
-                                    let respond = c.metadata.get("respond_with_credential");
-                                    out.push_str(&format!(
-                                        " - {} (id={}) active={} cd={}s warnonce={} respond={:?}\n",
-                                        c.name,
-                                        c.command_id,
-                                        c.is_active,
-                                        c.cooldown_seconds,
-                                        warnonce,
-                                        respond
-                                    ));
-                                }
-                            }
-                            out
-                        }
+            }
+            
+            if commands.is_empty() {
+                return match args.get(1) {
+                    Some(platform) => format!("No commands found for platform '{}'.", platform),
+                    None => "No commands found on any platform.".to_string(),
+                };
+            }
+            
+            let total = commands.len();
+            let commands = page.apply(commands);
+            let shown = commands.len();
+            let mut out = String::new();
+            let mut current_platform = None;
+            for (plat, c) in commands {
+                if current_platform != Some(plat) {
+                    if current_platform.is_some() {
+                        out.push('\n');
                     }
-                    Err(e) => format!("Error listing commands: {}", e),
+                    out.push_str(&format!("Commands for platform '{}':\n", plat));
+                    current_platform = Some(plat);
                 }
+                let warnonce = c.metadata.get("cooldown_warn_once").map(|v| v == "true").unwrap_or(false);
+                let respond = c.metadata.get("respond_with_credential");
+                let private = c.metadata.get("reply_privately").is_some_and(|v| v == "true");
+                let threaded = c.metadata.get("reply_to_message").is_some_and(|v| v == "true");
+                let text = c.metadata.get("response").is_some_and(|v| !v.is_empty());
+                out.push_str(&format!(
+                    " - {} (id={}) active={} cd={}s{} warnonce={} respond={:?}{}{}{}\n",
+                    c.name,
+                    c.command_id,
+                    c.is_active,
+                    c.cooldown_seconds,
+                    format_extra_cooldowns(&c.metadata),
+                    warnonce,
+                    respond,
+                    if private { " private" } else { "" },
+                    if threaded { " reply" } else { "" },
+                    if text { " text" } else { "" }
+                ));
+            }
+            if let Some(footer) = page.footer(shown, total) {
+                out.push_str(&format!("\n{}\n", footer));
+            }
+            out
+        }
+
+        "stats" => {
+            if args.len() < 2 {
+                return "Usage: command stats <commandName> [platform] [days]".to_string();
+            }
+            let cmd_name = args[1];
+            let platform = args.get(2).copied().unwrap_or("twitch-irc");
+            let days = match args.get(3).map(|d| d.parse::<i64>()) {
+                None => 7,
+                Some(Ok(days)) if days > 0 => days,
+                Some(_) => return "Days must be a positive number.".to_string(),
+            };
+
+            match CommandCommands::get_command_stats(client, platform, cmd_name, days).await {
+                Ok(result) => format_command_stats(&result.data, days),
+                Err(e) => format!("Error fetching command stats: {}", e),
             }
         }
         
+        "import" => {
+            let dry_run = args.contains(&"--dry-run");
+            let args: Vec<&str> = args.iter().copied().filter(|a| *a != "--dry-run").collect();
+            if args.len() < 3 {
+                return "Usage: command import <nightbot|streamelements|fossabot> <file> [platform] [--dry-run]".to_string();
+            }
+            let content = match std::fs::read_to_string(args[2]) {
+                Ok(content) => content,
+                Err(e) => return format!("Could not read '{}': {}", args[2], e),
+            };
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
+
+            match CommandCommands::import_commands(client, args[1], content, platform, dry_run).await {
+                Ok(result) => format_import_report(&result.data, platform),
+                Err(e) => format!("Error importing commands: {}", e),
+            }
+        }
+
+        "setresponse" => {
+            let (platform, args) = match args.iter().position(|a| *a == "--platform") {
+                Some(i) if i + 1 < args.len() => {
+                    let mut rest = args.to_vec();
+                    let platform = rest.remove(i + 1);
+                    rest.remove(i);
+                    (platform, rest)
+                }
+                Some(_) => return "Missing value for --platform".to_string(),
+                None => ("twitch-irc", args.to_vec()),
+            };
+            if args.len() < 3 {
+                return "Usage: command setresponse <commandName> <text...|none> [--platform P]".to_string();
+            }
+            let text = args[2..].join(" ");
+            let text = if text.eq_ignore_ascii_case("none") { String::new() } else { text };
+
+            match CommandCommands::update_metadata(client, platform, args[1], "response", text.clone()).await {
+                Ok(result) if text.is_empty() => format!(
+                    "'{}' on platform '{}' no longer has a text response.",
+                    result.data.command.name,
+                    platform
+                ),
+                Ok(result) => format!(
+                    "'{}' on platform '{}' now answers: {}",
+                    result.data.command.name,
+                    platform,
+                    text
+                ),
+                Err(e) => format!("Error updating response: {}", e),
+            }
+        }
+
         "setcooldown" => {
             if args.len() < 3 {
                 return "Usage: command setcooldown <commandName> <seconds> [platform]".to_string();
@@ -99,6 +172,134 @@ pub async fn handle_command_command(args: &[&str], client: &GrpcClient) -> Strin
             }
         }
         
+        "setusercooldown" => {
+            if args.len() < 3 {
+                return "Usage: command setusercooldown <commandName> <seconds> [platform]".to_string();
+            }
+            let seconds = match args[2].parse::<i32>() {
+                Ok(s) if s >= 0 => s,
+                _ => return "Cooldown seconds must be a non-negative integer.".to_string(),
+            };
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
+
+            match CommandCommands::update_metadata(client, platform, args[1], "user_cooldown_seconds", seconds.to_string()).await {
+                Ok(result) => format!(
+                    "Updated per-user cooldown for '{}' on platform '{}' to {} seconds.",
+                    result.data.command.name,
+                    platform,
+                    seconds
+                ),
+                Err(e) => format!("Error updating cooldown: {}", e),
+            }
+        }
+
+        "setrolecooldown" => {
+            if args.len() < 4 {
+                return "Usage: command setrolecooldown <commandName> <role> <seconds> [platform]".to_string();
+            }
+            let role = args[2].to_lowercase();
+            let seconds = match args[3].parse::<i32>() {
+                Ok(s) if s >= 0 => s,
+                _ => return "Cooldown seconds must be a non-negative integer.".to_string(),
+            };
+            let platform = args.get(4).copied().unwrap_or("twitch-irc");
+
+            let mut cooldowns = match CommandCommands::find_command_by_name(client, platform, args[1]).await {
+                Ok(Some(cmd)) => parse_role_cooldowns(cmd.metadata.get("role_cooldowns").map(String::as_str).unwrap_or(""))
+                    .unwrap_or_default(),
+                Ok(None) => return format!("Command '{}' not found on platform '{}'.", args[1], platform),
+                Err(e) => return format!("Error finding command: {}", e),
+            };
+            if seconds == 0 {
+                cooldowns.remove(&role);
+            } else {
+                cooldowns.insert(role.clone(), seconds);
+            }
+
+            match CommandCommands::update_metadata(client, platform, args[1], "role_cooldowns", format_role_cooldowns(&cooldowns)).await {
+                Ok(result) if seconds == 0 => format!(
+                    "Removed the '{}' cooldown from '{}' on platform '{}'.",
+                    role,
+                    result.data.command.name,
+                    platform
+                ),
+                Ok(result) => format!(
+                    "Updated '{}' cooldown for '{}' on platform '{}' to {} seconds.",
+                    role,
+                    result.data.command.name,
+                    platform,
+                    seconds
+                ),
+                Err(e) => format!("Error updating cooldown: {}", e),
+            }
+        }
+
+        "setbypassmods" => {
+            if args.len() < 3 {
+                return "Usage: command setbypassmods <commandName> <true|false> [platform]".to_string();
+            }
+            let bypass = match args[2].to_lowercase().as_str() {
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" => false,
+                _ => return "Bypass must be 'true' or 'false'.".to_string(),
+            };
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
+
+            match CommandCommands::update_metadata(client, platform, args[1], "cooldown_bypass_mods", bypass.to_string()).await {
+                Ok(result) => format!(
+                    "Moderators {} cooldowns of '{}' on platform '{}'.",
+                    if bypass { "now skip" } else { "now wait for" },
+                    result.data.command.name,
+                    platform
+                ),
+                Err(e) => format!("Error updating bypass: {}", e),
+            }
+        }
+
+        "setprivate" => {
+            if args.len() < 3 {
+                return "Usage: command setprivate <commandName> <true|false> [platform]".to_string();
+            }
+            let private = match args[2].to_lowercase().as_str() {
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" => false,
+                _ => return "Value must be 'true' or 'false'.".to_string(),
+            };
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
+
+            match CommandCommands::update_metadata(client, platform, args[1], "reply_privately", private.to_string()).await {
+                Ok(result) => format!(
+                    "'{}' on platform '{}' now replies {}.",
+                    result.data.command.name,
+                    platform,
+                    if private { "by whisper" } else { "in chat" }
+                ),
+                Err(e) => format!("Error updating reply mode: {}", e),
+            }
+        }
+
+        "setreply" => {
+            if args.len() < 3 {
+                return "Usage: command setreply <commandName> <true|false> [platform]".to_string();
+            }
+            let reply = match args[2].to_lowercase().as_str() {
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" => false,
+                _ => return "Value must be 'true' or 'false'.".to_string(),
+            };
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
+
+            match CommandCommands::update_metadata(client, platform, args[1], "reply_to_message", reply.to_string()).await {
+                Ok(result) => format!(
+                    "'{}' on platform '{}' now answers {}.",
+                    result.data.command.name,
+                    platform,
+                    if reply { "as a reply to the triggering message" } else { "with a plain chat message" }
+                ),
+                Err(e) => format!("Error updating reply mode: {}", e),
+            }
+        }
+
         "setwarnonce" => {
             if args.len() < 3 {
                 return "Usage: command setwarnonce <commandName> <true|false> [platform]".to_string();
@@ -144,19 +345,18 @@ pub async fn handle_command_command(args: &[&str], client: &GrpcClient) -> Strin
             
             match CommandCommands::update_respond_with(client, platform, cmd_name, credential_id.clone()).await {
                 Ok(result) => {
-                    if credential_id.is_none() {
-                        format!(
+                    match credential_id {
+                        None => format!(
                             "Cleared respond_with_credential for '{}' on platform '{}'.",
                             result.data.command.name,
                             platform
-                        )
-                    } else {
-                        format!(
+                        ),
+                        Some(credential_id) => format!(
                             "Updated respond_with_credential for '{}' on platform '{}' to '{}'.",
                             result.data.command.name,
                             platform,
-                            credential_id.unwrap()
-                        )
+                            credential_id
+                        ),
                     }
                 }
                 Err(e) => format!("Error updating respond_with_credential: {}", e),
@@ -173,7 +373,7 @@ pub async fn handle_command_command(args: &[&str], client: &GrpcClient) -> Strin
             
             // Find the command on old platform
             match CommandCommands::find_command_by_name(client, old_platform, cmd_name).await {
-                Ok(Some(mut cmd)) => {
+                Ok(Some(cmd)) => {
                     // Delete from old platform
                     if let Err(e) = CommandCommands::delete_command(client, &cmd.command_id).await {
                         return format!("Error deleting command from old platform: {}", e);
@@ -181,7 +381,7 @@ pub async fn handle_command_command(args: &[&str], client: &GrpcClient) -> Strin
                     
                     // Create on new platform
                     let plugin_id = cmd.metadata.get("plugin_id").map(|s| s.as_str());
-                    let warnonce = cmd.metadata.get("cooldown_warnonce").map(|v| v == "true").unwrap_or(false);
+                    let warnonce = cmd.metadata.get("cooldown_warn_once").map(|v| v == "true").unwrap_or(false);
                     let respond_cred = cmd.metadata.get("respond_with_credential").map(|s| s.as_str());
                     
                     match CommandCommands::create_command(
@@ -307,6 +507,108 @@ pub async fn handle_command_command(args: &[&str], client: &GrpcClient) -> Strin
             }
         }
         
-        _ => "Usage: command <list|setcooldown|setwarnonce|setrespond|setplatform|enable|disable|create|delete> [args...]".to_string(),
+        _ => "Usage: command <list|stats|import|setresponse|setcooldown|setusercooldown|setrolecooldown|setbypassmods|setprivate|setreply|setwarnonce|setrespond|setplatform|enable|disable|create|delete> [args...]".to_string(),
+    }
+}
+
+/// The `command import` report: what was (or would be) created, what was
+/// left alone and which variables have no equivalent.
+fn format_import_report(report: &ImportCommandsResponse, platform: &str) -> String {
+    let count = |status: &str| report.commands.iter().filter(|c| c.status == status).count();
+    let mut out = if report.dry_run {
+        format!("Dry run: {} command(s) would be created on '{}'.\n", count("new"), platform)
+    } else {
+        format!("Created {} command(s) on '{}'.\n", count("created"), platform)
+    };
+    for c in &report.commands {
+        let note = match c.status.as_str() {
+            "exists" => " (exists, left alone)",
+            "builtin" => " (built-in name, skipped)",
+            _ => "",
+        };
+        out.push_str(&format!(
+            " - !{} role={} cd={}s user-cd={}s{}{}: {}\n",
+            c.name,
+            c.min_role,
+            c.cooldown_seconds,
+            c.user_cooldown_seconds,
+            if c.enabled { "" } else { " disabled" },
+            note,
+            c.response
+        ));
+    }
+    if !report.skipped.is_empty() {
+        out.push_str("\nSkipped:\n");
+        for s in &report.skipped {
+            out.push_str(&format!(" - {}\n", s));
+        }
+    }
+    if !report.mapped.is_empty() {
+        let mapped: Vec<String> = report.mapped.iter()
+            .map(|m| format!("{} => {}", m.variable, m.placeholder))
+            .collect();
+        out.push_str(&format!("\nTranslated variables: {}\n", mapped.join(", ")));
+    }
+    if !report.unsupported.is_empty() {
+        out.push_str("\nUnsupported variables (left in the text as they are):\n");
+        for u in &report.unsupported {
+            out.push_str(&format!(" - {} used by {}\n", u.variable, u.used_by.join(", ")));
+        }
+    }
+    if !report.timers.is_empty() {
+        out.push_str("\nTimers (not imported; there are no timed messages yet):\n");
+        for t in &report.timers {
+            let every = if t.interval_minutes > 0 { format!("every {} min", t.interval_minutes) } else { "interval unknown".to_string() };
+            out.push_str(&format!(" - {} ({}): {}\n", t.name, every, t.messages.join(" | ")));
+        }
+    }
+    out
+}
+
+/// " user-cd=Ns role-cd=subscriber=N,vip=N mods-bypass", for whichever are set.
+fn format_extra_cooldowns(metadata: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    if let Some(secs) = metadata.get("user_cooldown_seconds").filter(|s| s.as_str() != "0") {
+        out.push_str(&format!(" user-cd={}s", secs));
+    }
+    if let Some(roles) = metadata.get("role_cooldowns").filter(|s| !s.is_empty()) {
+        out.push_str(&format!(" role-cd={}", roles));
+    }
+    if metadata.get("cooldown_bypass_mods").is_some_and(|v| v == "true") {
+        out.push_str(" mods-bypass");
+    }
+    out
+}
+
+/// The `command stats` report.
+fn format_command_stats(stats: &GetCommandStatsResponse, days: i64) -> String {
+    let mut out = format!(
+        "Stats for '{}' on '{}' (last {} day{}):\n",
+        stats.command_name,
+        stats.platform,
+        days,
+        if days == 1 { "" } else { "s" }
+    );
+    if stats.uses == 0 {
+        out.push_str("  Not used in this period.\n");
+        return out;
+    }
+    let error_rate = stats.errors as f64 * 100.0 / stats.uses as f64;
+    out.push_str(&format!("  Uses:     {}\n", stats.uses));
+    out.push_str(&format!("  Errors:   {} ({:.1}%)\n", stats.errors, error_rate));
+    if stats.timed_uses > 0 {
+        out.push_str(&format!(
+            "  Latency:  avg {:.0}ms  p50 {:.0}ms  p95 {:.0}ms  p99 {:.0}ms  max {:.0}ms ({} timed)\n",
+            stats.avg_ms, stats.p50_ms, stats.p95_ms, stats.p99_ms, stats.max_ms, stats.timed_uses
+        ));
+    }
+    if let Some(last) = stats.last_used.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+    {
+        out.push_str(&format!("  Last use: {}\n", last.format("%Y-%m-%d %H:%M UTC")));
+    }
+    if !stats.last_error.is_empty() {
+        out.push_str(&format!("  Last error: {}\n", stats.last_error));
     }
-}
\ No newline at end of file
+    out
+}
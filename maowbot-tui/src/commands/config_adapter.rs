@@ -1,9 +1,12 @@
 // Config command adapter for TUI
 use maowbot_common_ui::{GrpcClient, commands::config::ConfigCommands};
+use maowbot_proto::maowbot::services::BackupCounts;
 use std::fs;
+use std::io::{stdin, stdout, Write};
 use std::path::Path;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use super::paging::PageArgs;
 
 pub async fn handle_config_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
@@ -12,15 +15,24 @@ pub async fn handle_config_command(args: &[&str], client: &GrpcClient) -> String
 
     match args[0].to_lowercase().as_str() {
         "l" | "list" => {
+            let page = match PageArgs::extract(args) {
+                Ok((page, _)) => page,
+                Err(e) => return format!("Error: {}", e),
+            };
             match ConfigCommands::list_configs(client).await {
                 Ok(result) => {
                     if result.configs.is_empty() {
                         "No config values found in bot_config table.".to_string()
                     } else {
+                        let total = result.configs.len();
+                        let configs = page.apply(result.configs);
                         let mut out = String::new();
-                        for config in result.configs {
+                        for config in &configs {
                             out.push_str(&format!("{} = {}\n", config.key, config.value));
                         }
+                        if let Some(footer) = page.footer(configs.len(), total) {
+                            out.push_str(&format!("{}\n", footer));
+                        }
                         out
                     }
                 }
@@ -47,6 +59,19 @@ pub async fn handle_config_command(args: &[&str], client: &GrpcClient) -> String
             }
         }
 
+        "set-secret" => {
+            if args.len() < 3 {
+                return "Usage: config set-secret <key> <value>".to_string();
+            }
+            let key = args[1];
+            let value = args[2..].join(" ");
+
+            match ConfigCommands::set_secret(client, key, &value).await {
+                Ok(()) => format!("Stored secret '{}' (encrypted; use it as {{secret:{}}}).", key, key),
+                Err(e) => format!("Error setting secret => {}", e),
+            }
+        }
+
         "d" | "delete" => {
             if args.len() < 2 {
                 return "Usage: config delete <key>".to_string();
@@ -71,8 +96,10 @@ pub async fn handle_config_command(args: &[&str], client: &GrpcClient) -> String
             }
         }
         
+        "schema" => schema(client, args.get(1).copied()).await,
+
         "export" => {
-            let filename = args.get(1).map(|s| *s).unwrap_or("bot_config_export.json");
+            let filename = args.get(1).copied().unwrap_or("bot_config_export.json");
             export_config(client, filename).await
         }
         
@@ -85,6 +112,33 @@ pub async fn handle_config_command(args: &[&str], client: &GrpcClient) -> String
             import_config(client, filename, merge).await
         }
 
+        "rotate-key" => {
+            let dry_run = args.contains(&"--dry-run");
+            let confirmed = args.contains(&"--yes") || args.contains(&"-y");
+            rotate_key(client, dry_run, confirmed).await
+        }
+
+        "export-all" => {
+            let default_name = format!("maowbot_backup_{}.json", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+            let filename = args.get(1).copied().unwrap_or(&default_name);
+            export_all(client, filename).await
+        }
+
+        "import-all" => {
+            let Some(filename) = args.get(1).copied() else {
+                return "Usage: config import-all <file> [--key <backup-key>] [--yes]".to_string();
+            };
+            let key = match args.iter().position(|a| *a == "--key") {
+                Some(i) => match args.get(i + 1) {
+                    Some(k) => Some(*k),
+                    None => return "Usage: config import-all <file> [--key <backup-key>] [--yes]".to_string(),
+                },
+                None => None,
+            };
+            let confirmed = args.contains(&"--yes") || args.contains(&"-y");
+            import_all(client, filename, key, confirmed).await
+        }
+
         _ => usage(),
     }
 }
@@ -95,12 +149,156 @@ fn usage() -> String {
     out.push_str("  config l|list                  # list all items from bot_config table\n");
     out.push_str("  config g|get <key>             # get value for key\n");
     out.push_str("  config s|set <key> <val>       # set key=value\n");
+    out.push_str("  config set-secret <key> <val>  # store an encrypted secret for {secret:<key>}\n");
     out.push_str("  config d|delete <key>          # remove row by key\n");
+    out.push_str("  config schema [category]       # known settings: type, default, range, current value\n");
     out.push_str("  config export [filename]       # export all configs to JSON file\n");
     out.push_str("  config import <file> [--merge] # import configs from JSON (--merge to keep existing)\n");
+    out.push_str("  config rotate-key [--dry-run] [--yes] # new master key, re-encrypt stored credentials\n");
+    out.push_str("  config export-all [file]       # full backup: users, credentials, commands, redeems, pipelines, configs\n");
+    out.push_str("  config import-all <file> [--key <backup-key>] [--yes] # restore a full backup\n");
+    out
+}
+
+async fn rotate_key(client: &GrpcClient, dry_run: bool, confirmed: bool) -> String {
+    if !dry_run && !confirmed {
+        println!("Generate a new master key and re-encrypt all stored credentials? (y/n): ");
+        print!("> ");
+        let _ = stdout().flush();
+
+        let mut line = String::new();
+        let _ = stdin().read_line(&mut line);
+
+        if line.trim().to_lowercase() != "y" {
+            return "Key rotation cancelled.".to_string();
+        }
+    }
+
+    match ConfigCommands::rotate_encryption_key(client, dry_run).await {
+        Ok(result) => {
+            let mut out = format!("{}\n", result.message);
+            out.push_str(&format!("Key stored in: {}\n", result.key_source));
+            for (column, rows) in &result.columns {
+                out.push_str(&format!("  {:40} {}\n", column, rows));
+            }
+            out
+        }
+        Err(e) => format!("Error rotating encryption key => {}", e),
+    }
+}
+
+async fn schema(client: &GrpcClient, category: Option<&str>) -> String {
+    let settings = match ConfigCommands::get_settings_schema(client, category).await {
+        Ok(settings) => settings,
+        Err(e) => return format!("Error getting settings schema => {}", e),
+    };
+    if settings.is_empty() {
+        return match category {
+            Some(c) => format!("No settings in category '{}'.", c),
+            None => "No settings known to the server.".to_string(),
+        };
+    }
+
+    let mut out = String::new();
+    let mut current_category = String::new();
+    for setting in &settings {
+        if setting.category != current_category {
+            current_category = setting.category.clone();
+            out.push_str(&format!("[{}]\n", current_category));
+        }
+        let mut constraints = vec![setting.value_type.clone()];
+        match (setting.min, setting.max) {
+            (Some(min), Some(max)) => constraints.push(format!("{}..={}", min, max)),
+            (Some(min), None) => constraints.push(format!(">= {}", min)),
+            (None, Some(max)) => constraints.push(format!("<= {}", max)),
+            (None, None) => {}
+        }
+        if !setting.allowed_values.is_empty() {
+            constraints.push(setting.allowed_values.join("|"));
+        }
+        if let Some(default) = &setting.default_value {
+            constraints.push(format!("default {}", default));
+        }
+        if setting.requires_restart {
+            constraints.push("restart".to_string());
+        }
+        let value = if setting.is_set {
+            setting.current_value.clone()
+        } else if setting.current_value.is_empty() {
+            "(unset)".to_string()
+        } else {
+            format!("{} (default)", setting.current_value)
+        };
+        out.push_str(&format!("  {} = {}\n", setting.key, value));
+        out.push_str(&format!("      {} [{}]\n", setting.description, constraints.join(", ")));
+    }
     out
 }
 
+fn format_backup_counts(counts: &BackupCounts) -> String {
+    format!(
+        "{} workspaces, {} users, {} identities, {} credentials, {} platform configs, \
+         {} config values, {} commands, {} redeems, {} pipelines",
+        counts.workspaces, counts.users, counts.identities, counts.credentials, counts.platform_configs,
+        counts.bot_config, counts.commands, counts.redeems, counts.pipelines
+    )
+}
+
+async fn export_all(client: &GrpcClient, filename: &str) -> String {
+    let result = match ConfigCommands::export_backup(client).await {
+        Ok(r) => r,
+        Err(e) => return format!("Error exporting backup => {}", e),
+    };
+    if let Err(e) = fs::write(filename, &result.archive) {
+        return format!("Error writing file '{}': {}", filename, e);
+    }
+
+    let mut out = format!("Backup written to '{}' (schema version {})\n", filename, result.schema_version);
+    out.push_str(&format!("  {}\n", format_backup_counts(&result.counts)));
+    out.push_str(&format!("\nBackup key: {}\n", result.backup_key));
+    out.push_str("Store this key somewhere safe; it is not saved anywhere and is needed to restore credentials.\n");
+    out
+}
+
+async fn import_all(client: &GrpcClient, filename: &str, key: Option<&str>, confirmed: bool) -> String {
+    let archive = match fs::read(filename) {
+        Ok(data) => data,
+        Err(e) => return format!("Error reading file '{}': {}", filename, e),
+    };
+
+    if !confirmed {
+        if key.is_none() {
+            println!("No --key given: credentials and platform client secrets will not be restored.");
+        }
+        println!("Restore '{}' into this server? Existing entries are kept; config values are overwritten. (y/n): ", filename);
+        print!("> ");
+        let _ = stdout().flush();
+
+        let mut line = String::new();
+        let _ = stdin().read_line(&mut line);
+
+        if line.trim().to_lowercase() != "y" {
+            return "Import cancelled.".to_string();
+        }
+    }
+
+    match ConfigCommands::import_backup(client, archive, key).await {
+        Ok(result) => {
+            let mut out = format!("Restored backup (schema version {}):\n", result.schema_version);
+            out.push_str(&format!("  restored: {}\n", format_backup_counts(&result.restored)));
+            out.push_str(&format!("  skipped (already present): {}\n", format_backup_counts(&result.skipped)));
+            if !result.warnings.is_empty() {
+                out.push_str(&format!("{} warnings:\n", result.warnings.len()));
+                for w in &result.warnings {
+                    out.push_str(&format!("  - {}\n", w));
+                }
+            }
+            out
+        }
+        Err(e) => format!("Error importing backup => {}", e),
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct ConfigExport {
     version: String,
@@ -177,13 +375,10 @@ async fn import_config(client: &GrpcClient, filename: &str, merge: bool) -> Stri
     // Import each config
     for (key, value) in export_data.configs {
         if merge {
-            // Check if key exists
-            match ConfigCommands::get_config(client, &key).await {
-                Ok(_) => {
-                    skipped += 1;
-                    continue;
-                }
-                Err(_) => {}, // Key doesn't exist, proceed with import
+            // Skip keys that already exist
+            if ConfigCommands::get_config(client, &key).await.is_ok() {
+                skipped += 1;
+                continue;
             }
         }
         
@@ -1,18 +1,31 @@
 // Connection command adapter for TUI - consolidates start/stop/autostart/chat
 use maowbot_common_ui::{GrpcClient, commands::connectivity::ConnectivityCommands};
+use maowbot_common_ui::commands::{account::AccountCommands, command::CommandCommands};
 use crate::tui_module_simple::SimpleTuiModule;
 use std::sync::Arc;
 use maowbot_proto::maowbot::services::{
-    ListActiveRuntimesRequest, ListCredentialsRequest,
+    ListActiveRuntimesRequest, ListCredentialsRequest, RuntimeInfo,
 };
 
+/// Bracketed status for a running account, using the connection state the server reports.
+fn runtime_status_label(runtime: &RuntimeInfo) -> String {
+    let attempts = runtime.platform_specific.get("reconnect_attempts").map(String::as_str).unwrap_or("0");
+    let last_error = runtime.platform_specific.get("last_error").map(String::as_str).unwrap_or("connection lost");
+    match runtime.platform_specific.get("connection_state").map(String::as_str) {
+        Some("degraded") => format!("DEGRADED - {}s, {} reconnect(s)", runtime.uptime_seconds, attempts),
+        Some("reconnecting") => format!("RECONNECTING - attempt {}: {}", attempts, last_error),
+        Some("failed") => format!("FAILED - {}", last_error),
+        _ => format!("CONNECTED - {}s", runtime.uptime_seconds),
+    }
+}
+
 pub async fn handle_connection_command(
     args: &[&str], 
     client: &GrpcClient, 
     tui_module: &Arc<SimpleTuiModule>
 ) -> String {
     if args.is_empty() {
-        return "Usage: connection <start|stop|autostart|chat|status> [options]".to_string();
+        return "Usage: connection <start|stop|autostart|chat|status|identity> [options]".to_string();
     }
 
     match args[0] {
@@ -21,7 +34,7 @@ pub async fn handle_connection_command(
                 return "Usage: connection start <platform> [account]".to_string();
             }
             let platform = args[1];
-            let account = args.get(2).map(|s| *s).unwrap_or("");
+            let account = args.get(2).copied().unwrap_or("");
             
             // Handle OBS instances specially
             if platform == "obs" {
@@ -36,7 +49,7 @@ pub async fn handle_connection_command(
                 };
                 
                 match ConnectivityCommands::start_platform(client, platform, &instance_account).await {
-                    Ok(result) => return format!("Started OBS instance {}", account),
+                    Ok(_result) => return format!("Started OBS instance {}", account),
                     Err(e) => return format!("Error starting OBS instance: {}", e),
                 }
             }
@@ -111,7 +124,7 @@ pub async fn handle_connection_command(
                 return "Usage: connection stop <platform> [account]".to_string();
             }
             let platform = args[1];
-            let account = args.get(2).map(|s| *s).unwrap_or("");
+            let account = args.get(2).copied().unwrap_or("");
             
             if account.is_empty() {
                 return "Please specify an account to stop.".to_string();
@@ -127,7 +140,7 @@ pub async fn handle_connection_command(
                 };
                 
                 match ConnectivityCommands::stop_platform(client, platform, &instance_account).await {
-                    Ok(result) => return format!("Stopped OBS instance {}", account),
+                    Ok(_result) => return format!("Stopped OBS instance {}", account),
                     Err(e) => return format!("Error stopping OBS instance: {}", e),
                 }
             }
@@ -206,8 +219,8 @@ pub async fn handle_connection_command(
             
             match args[1] {
                 "on" => {
-                    let platform = args.get(2).map(|s| *s);
-                    let account = args.get(3).map(|s| *s);
+                    let platform = args.get(2).copied();
+                    let account = args.get(3).copied();
                     
                     match (platform, account) {
                         (Some(p), Some(a)) => {
@@ -274,7 +287,7 @@ pub async fn handle_connection_command(
             }
             
             // Group by platform
-            let platforms = ["TwitchIrc", "TwitchEventSub", "Discord", "VRChat", "OBS"];
+            let platforms = ["TwitchIrc", "TwitchEventSub", "Discord", "VRChat", "OBS", "Kick"];
             
             for platform_name in &platforms {
                 output.push_str(&format!("{}:\n", platform_name));
@@ -290,6 +303,7 @@ pub async fn handle_connection_command(
                                     maowbot_proto::maowbot::common::Platform::Discord => "Discord",
                                     maowbot_proto::maowbot::common::Platform::Vrchat => "VRChat",
                                     maowbot_proto::maowbot::common::Platform::Obs => "OBS",
+                                    maowbot_proto::maowbot::common::Platform::Kick => "Kick",
                                     _ => return false,
                                 };
                                 display_name == *platform_name
@@ -322,6 +336,7 @@ pub async fn handle_connection_command(
                                 "Discord" => "discord",
                                 "VRChat" => "vrchat",
                                 "OBS" => "obs",
+                                "Kick" => "kick",
                                 _ => platform_name,
                             };
                             
@@ -362,8 +377,8 @@ pub async fn handle_connection_command(
                                                    (rt.account_name == *username || rt.account_name == *user_id))
                                         .unwrap();
                                     output.push_str(&format!(
-                                        "  - {} ({}) [CONNECTED - {}s]\n",
-                                        username, display_name, runtime.uptime_seconds
+                                        "  - {} ({}) [{}]\n",
+                                        username, display_name, runtime_status_label(runtime)
                                     ));
                                 } else {
                                     output.push_str(&format!(
@@ -381,6 +396,90 @@ pub async fn handle_connection_command(
             output
         }
         
+        "identity" => handle_identity(&args[1..], client).await,
+
         _ => format!("Unknown connection subcommand: {}", args[0]),
     }
+}
+
+const IDENTITY_USAGE: &str = "Usage:\n  connection identity list [platform]\n  connection identity set <platform> <account> [channel <name> | command <name>]\n  connection identity clear <platform> [channel <name> | command <name>]\n";
+
+/// Which account speaks where: per channel (or the platform default, "*"),
+/// or per command through its respond-with credential.
+async fn handle_identity(args: &[&str], client: &GrpcClient) -> String {
+    match args.first().copied() {
+        Some("list") => {
+            let platform = args.get(1).copied().unwrap_or("");
+            match AccountCommands::list_chat_identities(client, platform).await {
+                Ok(identities) if identities.is_empty() => {
+                    "No identities assigned; each platform's bot account (else the broadcaster) speaks.".to_string()
+                }
+                Ok(identities) => {
+                    let mut out = String::from("Chat identities:\n");
+                    for i in &identities {
+                        let channel = if i.channel == "*" { "(default)".to_string() } else { format!("#{}", i.channel) };
+                        let missing = if i.credential_id.is_empty() { "  [no such account]" } else { "" };
+                        out.push_str(&format!("  {:<16} {:<24} {}{}\n", i.platform, channel, i.account, missing));
+                    }
+                    out
+                }
+                Err(e) => format!("Error listing identities => {}", e),
+            }
+        }
+
+        Some(action @ ("set" | "clear")) => {
+            let (platform, account, target) = match (action, args.get(1)) {
+                ("set", Some(platform)) => match args.get(2) {
+                    Some(account) => (*platform, Some(*account), &args[3..]),
+                    None => return IDENTITY_USAGE.to_string(),
+                },
+                ("clear", Some(platform)) => (*platform, None, &args[2..]),
+                _ => return IDENTITY_USAGE.to_string(),
+            };
+
+            match target {
+                [] | ["default"] => set_channel_identity(client, platform, "*", account).await,
+                ["channel", channel] => set_channel_identity(client, platform, channel, account).await,
+                ["command", command] => set_command_identity(client, platform, command, account).await,
+                _ => IDENTITY_USAGE.to_string(),
+            }
+        }
+
+        _ => IDENTITY_USAGE.to_string(),
+    }
+}
+
+async fn set_channel_identity(client: &GrpcClient, platform: &str, channel: &str, account: Option<&str>) -> String {
+    match AccountCommands::set_chat_identity(client, platform, channel, account).await {
+        Ok(identity) => {
+            let place = if identity.channel == "*" {
+                format!("{} by default", identity.platform)
+            } else {
+                format!("#{} on {}", identity.channel, identity.platform)
+            };
+            if identity.account.is_empty() {
+                format!("Cleared the identity for {}.", place)
+            } else {
+                format!("{} now speaks in {}.", identity.account, place)
+            }
+        }
+        Err(e) => format!("Error setting identity => {}", e),
+    }
+}
+
+async fn set_command_identity(client: &GrpcClient, platform: &str, command: &str, account: Option<&str>) -> String {
+    let credential_id = match account {
+        Some(account) => match AccountCommands::find_credential(client, platform, account).await {
+            Ok(cred) => Some(cred.credential_id),
+            Err(e) => return format!("Error finding account => {}", e),
+        },
+        None => None,
+    };
+    match CommandCommands::update_respond_with(client, platform, command, credential_id).await {
+        Ok(result) => match account {
+            Some(account) => format!("{} now answers '{}' on {}.", account, result.data.command.name, platform),
+            None => format!("'{}' on {} answers as the channel's identity again.", result.data.command.name, platform),
+        },
+        Err(e) => format!("Error updating command => {}", e),
+    }
 }
\ No newline at end of file
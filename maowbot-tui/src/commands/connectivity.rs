@@ -103,7 +103,7 @@ async fn handle_start_cmd(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
             Err(e) => return format!("Error listing credentials => {:?}", e),
         };
         if all_creds.is_empty() {
-            return format!("No accounts found for platform='{}'. Cannot start.", platform_str);
+            format!("No accounts found for platform='{}'. Cannot start.", platform_str)
         } else if all_creds.len() == 1 {
             // Exactly one => proceed
             let c = &all_creds[0];
@@ -182,6 +182,7 @@ async fn handle_start_cmd(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
 /// - broadcaster => user_name="cuteStreamer"
 /// - bot => user_name="myBot"
 /// - teammate => user_name="otherModerator"
+///
 /// Then whichever we start, it does: JOIN #cuteStreamer, JOIN #myBot, JOIN #otherModerator
 /// except for itself.
 async fn auto_join_all_other_twitch_accounts(
@@ -87,7 +87,7 @@ async fn handle_start_cmd(args: &[&str], client: &GrpcClient) -> String {
         };
         
         if accounts.is_empty() {
-            return format!("No accounts found for platform='{}'. Cannot start.", platform_str);
+            format!("No accounts found for platform='{}'. Cannot start.", platform_str)
         } else if accounts.len() == 1 {
             // Exactly one => proceed
             let acc = &accounts[0];
@@ -178,7 +178,7 @@ async fn handle_stop_cmd(args: &[&str], client: &GrpcClient) -> String {
         };
         
         if accounts.is_empty() {
-            return format!("No accounts found for platform='{}'. Cannot stop.", platform_str);
+            format!("No accounts found for platform='{}'. Cannot stop.", platform_str)
         } else if accounts.len() == 1 {
             let acc = &accounts[0];
             match ConnectivityCommands::stop_platform(client, platform_str, &acc.display_name).await {
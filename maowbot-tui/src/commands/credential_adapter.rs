@@ -2,10 +2,13 @@
 use maowbot_common_ui::GrpcClient;
 use maowbot_proto::maowbot::services::{
     ListCredentialsRequest, RefreshCredentialRequest, RevokeCredentialRequest,
-    GetCredentialHealthRequest, BatchRefreshCredentialsRequest,
-    credential_service_client::CredentialServiceClient,
+    GetCredentialHealthRequest, BatchRefreshCredentialsRequest, BatchValidateCredentialsRequest,
 };
 use maowbot_proto::maowbot::common::Platform;
+use super::paging::PageArgs;
+
+/// Credentials expiring within this many hours are flagged by `credential health`
+const DEFAULT_EXPIRY_WARNING_HOURS: i32 = 72;
 
 pub async fn handle_credential_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
@@ -14,8 +17,12 @@ pub async fn handle_credential_command(args: &[&str], client: &GrpcClient) -> St
 
     match args[0] {
         "list" => {
+            let (page, args) = match PageArgs::extract(args) {
+                Ok(parsed) => parsed,
+                Err(e) => return format!("Error: {}", e),
+            };
             let platform = args.get(1).and_then(|p| parse_platform(p).ok());
-            list_credentials(client, platform).await
+            list_credentials(client, platform, page).await
         }
         
         "refresh" => {
@@ -34,8 +41,28 @@ pub async fn handle_credential_command(args: &[&str], client: &GrpcClient) -> St
         }
         
         "health" => {
-            let platform = args.get(1).and_then(|p| parse_platform(p).ok());
-            get_credential_health(client, platform).await
+            let mut platform = None;
+            let mut check = true;
+            let mut hours = DEFAULT_EXPIRY_WARNING_HOURS;
+            let mut i = 1;
+            while i < args.len() {
+                match args[i] {
+                    "--no-check" => check = false,
+                    "--hours" => {
+                        hours = match args.get(i + 1).and_then(|h| h.parse::<i32>().ok()) {
+                            Some(h) if h > 0 => h,
+                            _ => return "Usage: credential health [platform] [--hours N] [--no-check]".to_string(),
+                        };
+                        i += 1;
+                    }
+                    other => match parse_platform(other) {
+                        Ok(p) => platform = Some(p),
+                        Err(e) => return format!("Invalid platform: {}", e),
+                    },
+                }
+                i += 1;
+            }
+            get_credential_health(client, platform, hours, check).await
         }
         
         "batch-refresh" => {
@@ -54,7 +81,7 @@ pub async fn handle_credential_command(args: &[&str], client: &GrpcClient) -> St
     }
 }
 
-async fn list_credentials(client: &GrpcClient, platform: Option<Platform>) -> String {
+async fn list_credentials(client: &GrpcClient, platform: Option<Platform>, page: PageArgs) -> String {
     let request = ListCredentialsRequest {
         platforms: platform.map(|p| vec![p as i32]).unwrap_or_default(),
         active_only: false,
@@ -69,7 +96,10 @@ async fn list_credentials(client: &GrpcClient, platform: Option<Platform>) -> St
             if creds.is_empty() {
                 "No credentials found.".to_string()
             } else {
-                let mut output = format!("Found {} credential(s):\n", creds.len());
+                let total = creds.len();
+                let creds = page.apply(creds);
+                let shown = creds.len();
+                let mut output = format!("Found {} credential(s):\n", total);
                 for info in creds {
                     if let Some(cred) = info.credential {
                         let platform_name = format_platform(cred.platform);
@@ -93,6 +123,9 @@ async fn list_credentials(client: &GrpcClient, platform: Option<Platform>) -> St
                         output.push_str(&format!("    ID: {}\n", cred.credential_id));
                     }
                 }
+                if let Some(footer) = page.footer(shown, total) {
+                    output.push_str(&format!("{}\n", footer));
+                }
                 output
             }
         }
@@ -137,39 +170,125 @@ async fn revoke_credential(client: &GrpcClient, credential_id: &str, revoke_at_p
     }
 }
 
-async fn get_credential_health(client: &GrpcClient, platform: Option<Platform>) -> String {
-    let request = GetCredentialHealthRequest {
-        platforms: platform.map(|p| vec![p as i32]).unwrap_or_default(),
-    };
-    
+async fn get_credential_health(
+    client: &GrpcClient,
+    platform: Option<Platform>,
+    expiring_within_hours: i32,
+    check: bool,
+) -> String {
+    let platforms = platform.map(|p| vec![p as i32]).unwrap_or_default();
     let mut cred_client = client.credential.clone();
-    match cred_client.get_credential_health(request).await {
-        Ok(response) => {
-            let health = response.into_inner();
-            let mut output = String::new();
-            
-            if let Some(overall) = health.overall {
+
+    let request = BatchValidateCredentialsRequest {
+        platforms: platforms.clone(),
+        expiring_within_hours,
+        skip_platform_check: !check,
+    };
+    let report = match cred_client.batch_validate_credentials(request).await {
+        Ok(response) => response.into_inner(),
+        Err(e) => return format!("Error validating credentials: {}", e),
+    };
+
+    let mut output = String::new();
+    if report.validations.is_empty() {
+        output.push_str("No credentials stored.\n");
+    } else {
+        output.push_str(&format!(
+            "{} credential(s): {} valid, {} invalid, {} expiring within {}h\n\n",
+            report.validations.len(),
+            report.valid_count,
+            report.invalid_count,
+            report.expiring_count,
+            expiring_within_hours
+        ));
+    }
+
+    for validation in &report.validations {
+        let Some(cred) = validation.credential.as_ref() else { continue };
+
+        let marker = if !validation.valid {
+            "✗"
+        } else if validation.expiring_soon {
+            "⚠"
+        } else {
+            "✓"
+        };
+        let role = if cred.is_bot {
+            " [bot]"
+        } else if cred.is_broadcaster {
+            " [broadcaster]"
+        } else if cred.is_teammate {
+            " [teammate]"
+        } else {
+            ""
+        };
+        output.push_str(&format!(
+            "{} {} - {}{}\n",
+            marker,
+            format_platform(cred.platform),
+            cred.user_name,
+            role
+        ));
+        output.push_str(&format!("    ID:           {}\n", cred.credential_id));
+
+        let status = match (validation.valid, validation.checked) {
+            (true, true) => "valid".to_string(),
+            (true, false) if check => format!("not checked ({})", validation.error_message),
+            (true, false) => "not checked".to_string(),
+            (false, _) if !validation.error_message.is_empty() => format!("INVALID ({})", validation.error_message),
+            (false, _) => "INVALID".to_string(),
+        };
+        output.push_str(&format!("    Status:       {}\n", status));
+
+        let expiry = if cred.token_expires_at.is_none() {
+            "never".to_string()
+        } else if validation.seconds_until_expiry <= 0 {
+            format!("EXPIRED {} ago", format_countdown(-validation.seconds_until_expiry))
+        } else if validation.expiring_soon {
+            format!("in {}  <-- EXPIRING SOON", format_countdown(validation.seconds_until_expiry))
+        } else {
+            format!("in {}", format_countdown(validation.seconds_until_expiry))
+        };
+        output.push_str(&format!("    Expires:      {}\n", expiry));
+
+        let scopes = if cred.scopes.is_empty() { "-".to_string() } else { cred.scopes.join(" ") };
+        output.push_str(&format!("    Scopes:       {}\n", scopes));
+
+        let refreshed = cred.last_refreshed.as_ref()
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+            .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        output.push_str(&format!("    Refreshed:    {}\n", refreshed));
+    }
+
+    // Per-platform summary from the existing health RPC
+    if let Ok(response) = cred_client.get_credential_health(GetCredentialHealthRequest { platforms }).await {
+        let health = response.into_inner();
+        if let Some(overall) = health.overall {
+            if overall.total_platforms > 0 {
                 output.push_str(&format!(
-                    "Overall Health: {:.1}% ({}/{} platforms healthy)\n\n",
+                    "\nOverall Health: {:.1}% ({}/{} platforms healthy)\n",
                     overall.health_score * 100.0,
                     overall.healthy_platforms,
                     overall.total_platforms
                 ));
             }
-            
-            for platform_health in health.platform_health {
-                let platform_name = format_platform(platform_health.platform);
-                output.push_str(&format!("Platform: {}\n", platform_name));
-                output.push_str(&format!("  Total Credentials: {}\n", platform_health.total_credentials));
-                output.push_str(&format!("  Active: {}\n", platform_health.active_credentials));
-                output.push_str(&format!("  Expired: {}\n", platform_health.expired_credentials));
-                output.push_str(&format!("  Expiring Soon: {}\n", platform_health.expiring_soon));
-                output.push_str("\n");
-            }
-            
-            output
         }
-        Err(e) => format!("Error getting credential health: {}", e),
+    }
+
+    output
+}
+
+/// Format a duration in seconds as a short countdown, e.g. "2d 04h" or "35m".
+fn format_countdown(seconds: i64) -> String {
+    let (days, rem) = (seconds / 86_400, seconds % 86_400);
+    let (hours, minutes) = (rem / 3600, (rem % 3600) / 60);
+    if days > 0 {
+        format!("{}d {:02}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes.max(1))
     }
 }
 
@@ -233,6 +352,7 @@ fn parse_platform(platform_str: &str) -> Result<Platform, String> {
         "twitch-eventsub" => Ok(Platform::TwitchEventsub),
         "discord" => Ok(Platform::Discord),
         "vrchat" => Ok(Platform::Vrchat),
+        "kick" => Ok(Platform::Kick),
         _ => Err(format!("Unknown platform '{}'", platform_str)),
     }
 }
@@ -244,6 +364,7 @@ fn format_platform(platform: i32) -> &'static str {
         Ok(Platform::TwitchEventsub) => "Twitch-EventSub",
         Ok(Platform::Discord) => "Discord",
         Ok(Platform::Vrchat) => "VRChat",
+        Ok(Platform::Kick) => "Kick",
         _ => "Unknown",
     }
 }
\ No newline at end of file
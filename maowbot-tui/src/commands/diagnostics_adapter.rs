@@ -1,13 +1,15 @@
 // Diagnostics command adapter for TUI - system health, logs, and metrics
 use maowbot_common_ui::GrpcClient;
+use maowbot_common_ui::commands::plugin::PluginCommands;
+use maowbot_common_ui::commands::health::{self, HealthCommands};
 use maowbot_proto::maowbot::services::{
     GetSystemStatusRequest, GetCredentialHealthRequest,
-    ListActiveRuntimesRequest, ListPluginsRequest,
+    ListActiveRuntimesRequest, ListPluginsRequest, DiagnosticStatus,
 };
 
 pub async fn handle_diagnostics_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
-        return "Usage: diagnostics <health|status|metrics|logs|test> [options]".to_string();
+        return "Usage: diagnostics <health|run|status|ready|metrics|logs|test> [options]".to_string();
     }
 
     match args[0] {
@@ -15,10 +17,18 @@ pub async fn handle_diagnostics_command(args: &[&str], client: &GrpcClient) -> S
             get_system_health(client).await
         }
         
+        "run" => {
+            run_diagnostics(client, &args[1..]).await
+        }
+        
         "status" => {
             get_detailed_status(client).await
         }
         
+        "ready" => {
+            get_readiness(client).await
+        }
+        
         "metrics" => {
             get_system_metrics(client).await
         }
@@ -38,6 +48,39 @@ pub async fn handle_diagnostics_command(args: &[&str], client: &GrpcClient) -> S
     }
 }
 
+async fn run_diagnostics(client: &GrpcClient, categories: &[&str]) -> String {
+    let categories = categories.iter().map(|c| c.to_lowercase()).collect();
+    let report = match PluginCommands::run_diagnostics(client, categories).await {
+        Ok(report) => report,
+        Err(e) => return format!("Error running diagnostics: {}", e),
+    };
+    
+    let label = |status: i32| match DiagnosticStatus::try_from(status).unwrap_or(DiagnosticStatus::Ok) {
+        DiagnosticStatus::Ok => "OK  ",
+        DiagnosticStatus::Skipped => "SKIP",
+        DiagnosticStatus::Warning => "WARN",
+        DiagnosticStatus::Failed => "FAIL",
+    };
+    let mut output = String::new();
+    output.push_str("=== Diagnostics ===\n");
+    let mut category = "";
+    for check in &report.checks {
+        if check.category != category {
+            category = &check.category;
+            output.push_str(&format!("\n{}:\n", category));
+        }
+        let latency = if check.latency_ms > 0 { format!(" ({} ms)", check.latency_ms) } else { String::new() };
+        output.push_str(&format!("  [{}] {} - {}{}\n", label(check.status), check.name, check.message, latency));
+    }
+    output.push_str(&format!(
+        "\nOverall: {} ({} checks in {} ms)\n",
+        label(report.overall).trim(),
+        report.checks.len(),
+        report.duration_ms
+    ));
+    output
+}
+
 async fn get_system_health(client: &GrpcClient) -> String {
     let mut output = String::new();
     output.push_str("=== System Health Check ===\n\n");
@@ -51,7 +94,7 @@ async fn get_system_health(client: &GrpcClient) -> String {
     match plugin_client.get_system_status(plugin_request).await {
         Ok(response) => {
             let status = response.into_inner();
-            output.push_str(&format!("Plugin System: ✓ HEALTHY\n"));
+            output.push_str("Plugin System: ✓ HEALTHY\n");
             output.push_str(&format!("  Total Plugins: {}\n", status.total_plugins));
             output.push_str(&format!("  Active Plugins: {}\n", status.active_plugins));
             output.push_str(&format!("  System Uptime: {}s\n", status.uptime_seconds));
@@ -61,7 +104,7 @@ async fn get_system_health(client: &GrpcClient) -> String {
         }
     }
     
-    output.push_str("\n");
+    output.push('\n');
     
     // Check credential health
     let cred_request = GetCredentialHealthRequest {
@@ -100,7 +143,7 @@ async fn get_system_health(client: &GrpcClient) -> String {
         }
     }
     
-    output.push_str("\n");
+    output.push('\n');
     
     // Check runtime status
     let runtime_request = ListActiveRuntimesRequest {
@@ -114,10 +157,18 @@ async fn get_system_health(client: &GrpcClient) -> String {
             output.push_str(&format!("Active Runtimes: {}\n", runtimes.len()));
             
             for runtime in runtimes {
-                output.push_str(&format!("  ✓ {} - {} ({}s uptime)\n",
+                let state = runtime.platform_specific.get("connection_state").map(String::as_str);
+                let icon = match state {
+                    Some("degraded") | Some("reconnecting") => "⚠",
+                    Some("failed") => "✗",
+                    _ => "✓",
+                };
+                output.push_str(&format!("  {} {} - {} ({}s uptime{})\n",
+                    icon,
                     runtime.platform,
                     runtime.account_name,
-                    runtime.uptime_seconds
+                    runtime.uptime_seconds,
+                    state.map(|s| format!(", {}", s)).unwrap_or_default()
                 ));
             }
         }
@@ -169,7 +220,7 @@ async fn get_detailed_status(client: &GrpcClient) -> String {
         }
     }
     
-    output.push_str("\n");
+    output.push('\n');
     
     // Get platform runtime details
     let runtime_request = ListActiveRuntimesRequest {
@@ -186,6 +237,18 @@ async fn get_detailed_status(client: &GrpcClient) -> String {
                 output.push_str(&format!("  {} - {}\n", runtime.platform, runtime.account_name));
                 output.push_str(&format!("    Runtime ID: {}\n", runtime.runtime_id));
                 output.push_str(&format!("    Uptime: {}s\n", runtime.uptime_seconds));
+                if let Some(state) = runtime.platform_specific.get("connection_state") {
+                    output.push_str(&format!("    Connection: {}\n", state));
+                    if let Some(attempts) = runtime.platform_specific.get("reconnect_attempts") {
+                        output.push_str(&format!("    Reconnect Attempts: {}\n", attempts));
+                    }
+                    if let Some(err) = runtime.platform_specific.get("last_error") {
+                        output.push_str(&format!("    Last Error: {}\n", err));
+                    }
+                    if let Some(next) = runtime.platform_specific.get("next_retry_at") {
+                        output.push_str(&format!("    Next Retry: {}\n", next));
+                    }
+                }
                 
                 if let Some(stats) = runtime.stats {
                     output.push_str(&format!("    Messages Sent: {}\n", stats.messages_sent));
@@ -202,20 +265,54 @@ async fn get_detailed_status(client: &GrpcClient) -> String {
     output
 }
 
-async fn get_system_metrics(_client: &GrpcClient) -> String {
-    // This would require a metrics service in the proto files
-    // For now, return a placeholder
+async fn get_readiness(client: &GrpcClient) -> String {
+    let readiness = match HealthCommands::get_readiness(client).await {
+        Ok(readiness) => readiness,
+        Err(e) => return format!("Error getting readiness: {}", e),
+    };
+
+    let mut output = String::new();
+    output.push_str("=== Startup Readiness ===\n");
+    for s in &readiness.subsystems {
+        let took = if s.startup_ms > 0 { format!(" in {} ms", s.startup_ms) } else { String::new() };
+        let message = if s.message.is_empty() { String::new() } else { format!(" - {}", s.message) };
+        let deps = if s.depends_on.is_empty() { String::new() } else { format!(" (after {})", s.depends_on.join(", ")) };
+        output.push_str(&format!("  {:<13} {:<9}{}{}{}\n", s.name, health::state_name(s.state), took, message, deps));
+    }
+    if readiness.ready {
+        output.push_str(&format!("\nReady; up {}s\n", readiness.uptime_ms / 1000));
+    } else {
+        output.push_str(&format!("\nStill starting: {}\n", health::describe_waiting(&readiness)));
+    }
+    output
+}
+
+async fn get_system_metrics(client: &GrpcClient) -> String {
+    let sample = match PluginCommands::get_runtime_metrics(client, false).await {
+        Ok(sample) => sample,
+        Err(e) => return format!("Error fetching runtime metrics: {}", e),
+    };
     let mut output = String::new();
     output.push_str("=== System Metrics ===\n\n");
-    output.push_str("Metrics collection not yet implemented in gRPC services.\n");
-    output.push_str("\nSuggested metrics to track:\n");
-    output.push_str("  - Message throughput (msgs/sec)\n");
-    output.push_str("  - Command processing time (avg/p95/p99)\n");
-    output.push_str("  - Memory usage by component\n");
-    output.push_str("  - Database query performance\n");
-    output.push_str("  - API response times\n");
-    output.push_str("  - Error rates by platform\n");
-    
+    output.push_str(&format!("Uptime: {}s\n", sample.uptime_seconds));
+    output.push_str(&format!("Events published: {}\n", sample.events_total));
+    output.push_str(&format!(
+        "OSC packets: {} received, {} sent\n",
+        sample.osc_packets_received, sample.osc_packets_sent
+    ));
+
+    output.push_str("\nOutbound chat guard:\n");
+    match sample.outbound {
+        Some(outbound) => {
+            output.push_str(&format!("  Sent:                  {}\n", outbound.sent));
+            output.push_str(&format!("  Repeats dropped:       {}\n", outbound.duplicates_dropped));
+            output.push_str(&format!("  Rate-limited:          {}\n", outbound.rate_limited));
+            output.push_str(&format!("  Own messages ignored:  {}\n", outbound.own_messages_ignored));
+        }
+        None => output.push_str("  not running\n"),
+    }
+    output.push_str("\nUse 'watch status' for live rates.\n");
+
     output
 }
 
@@ -272,6 +369,7 @@ async fn run_connectivity_tests(client: &GrpcClient) -> String {
         }),
         order_by: String::new(),
         descending: false,
+        read_mask: None,
     };
     let mut user_client = client.user.clone();
     
@@ -377,7 +377,7 @@ async fn handle_discord_event_command(args: &[&str], bot_api: &Arc<dyn BotApi>)
 
             // Now call the API
             match bot_api
-                .add_discord_event_config(&event_name, &guild_str, &channel_arg, respond_with_cred)
+                .add_discord_event_config(event_name, &guild_str, channel_arg, respond_with_cred)
                 .await
             {
                 Ok(_) => format!(
@@ -435,7 +435,7 @@ async fn handle_discord_event_command(args: &[&str], bot_api: &Arc<dyn BotApi>)
             }
 
             match bot_api
-                .remove_discord_event_config(&event_name, &guild_str, &channel_arg, respond_with_cred)
+                .remove_discord_event_config(event_name, &guild_str, channel_arg, respond_with_cred)
                 .await
             {
                 Ok(_) => format!(
@@ -480,7 +480,7 @@ pub async fn handle_discord_command(args: &[&str], client: &GrpcClient) -> Strin
             match DiscordCommands::get_member(client, &account_name, guild_id, user_id).await {
                 Ok(result) => {
                     let member = &result.data.member;
-                    let mut out = format!("Discord Member Info:\n");
+                    let mut out = "Discord Member Info:\n".to_string();
                     out.push_str(&format!(" - User: {} (ID: {})\n", member.username, member.user_id));
                     out.push_str(&format!(" - Display Name: {}\n", member.display_name));
                     if let Some(joined_at) = &member.joined_at {
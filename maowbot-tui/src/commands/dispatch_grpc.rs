@@ -4,7 +4,6 @@ use crate::help;
 use crate::tui_module_simple::SimpleTuiModule;
 
 // Import the new adapters
-use super::user_adapter;
 use super::platform_adapter;
 use super::twitch_adapter;
 use super::test_grpc;
@@ -14,10 +13,22 @@ use super::redeem_adapter;
 use super::account_adapter;
 use super::ai_adapter;
 use super::config_adapter;
+use super::workspace_adapter;
+use super::token_adapter;
+use super::audit_adapter;
+use super::chatlog_adapter;
+use super::export_adapter;
+use super::giveaway_adapter;
+use super::vote_adapter;
+use super::protect_adapter;
+use super::automod_adapter;
+use super::responder_adapter;
+use super::alerting_adapter;
+use super::emotes_adapter;
+use super::session_adapter;
+use super::language_adapter;
 use super::plugin_adapter;
-use super::connectivity_adapter;
 use super::drip_adapter;
-use super::member_adapter;
 use super::osc_adapter;
 use super::vrchat_adapter;
 use super::obs_adapter;
@@ -27,12 +38,51 @@ use super::unified_user_adapter;
 use super::diagnostics_adapter;
 use super::system;
 use super::pipeline_adapter;
+use super::watch_adapter;
+use super::alias_adapter;
+use super::simulate_adapter;
+use super::test_harness::{self, TestHarnessCommand};
+
+/// Top-level command names handled below; aliases may not shadow these.
+pub const BUILTIN_COMMANDS: &[&str] = &[
+    "help", "user", "platform", "twitch", "command", "discord", "redeem", "account",
+    "credential", "ai", "config", "plugin", "list", "status", "connection", "autostart",
+    "start", "stop", "chat", "drip", "member", "osc", "vrchat", "obs", "test_grpc",
+    "system", "diagnostics", "diag", "pipeline", "watch", "alias", "simulate", "test_harness", "workspace", "token", "audit", "chatlog", "export", "giveaway", "vote", "protect", "automod", "responder", "alerting", "emotes", "session", "language", "quit", "ttv", "plug",
+];
 
 pub async fn dispatch_grpc(
     line: &str,
     client: &GrpcClient,
     tui_module: &Arc<SimpleTuiModule>,
     process_manager: &Arc<ProcessManager>,
+) -> (bool, Option<String>) {
+    // Expand user-defined aliases before running anything
+    let expanded = tui_module.aliases.lock().unwrap().expand(line);
+    let commands = match expanded {
+        Ok(Some(commands)) => commands,
+        Ok(None) => return dispatch_command(line, client, tui_module, process_manager).await,
+        Err(e) => return (false, Some(format!("Error: {}", e))),
+    };
+
+    let mut outputs = Vec::new();
+    for command in &commands {
+        let (quit_requested, output) = dispatch_command(command, client, tui_module, process_manager).await;
+        if let Some(msg) = output {
+            outputs.push(msg);
+        }
+        if quit_requested {
+            return (true, Some(outputs.join("\n")));
+        }
+    }
+    (false, Some(outputs.join("\n")))
+}
+
+async fn dispatch_command(
+    line: &str,
+    client: &GrpcClient,
+    tui_module: &Arc<SimpleTuiModule>,
+    process_manager: &Arc<ProcessManager>,
 ) -> (bool, Option<String>) {
     let parts: Vec<&str> = line.split_whitespace().collect();
     if parts.is_empty() {
@@ -43,7 +93,7 @@ pub async fn dispatch_grpc(
 
     match cmd.as_str() {
         "help" => {
-            let subcmd = args.get(0).map(|s| *s).unwrap_or("");
+            let subcmd = args.first().copied().unwrap_or("");
             let msg = help::show_command_help(subcmd);
             (false, Some(msg))
         }
@@ -98,6 +148,76 @@ pub async fn dispatch_grpc(
             (false, Some(msg))
         }
 
+        "workspace" => {
+            let msg = workspace_adapter::handle_workspace_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "token" => {
+            let msg = token_adapter::handle_token_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "audit" => {
+            let msg = audit_adapter::handle_audit_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "chatlog" => {
+            let msg = chatlog_adapter::handle_chatlog_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "export" => {
+            let msg = export_adapter::handle_export_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "giveaway" => {
+            let msg = giveaway_adapter::handle_giveaway_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "vote" => {
+            let msg = vote_adapter::handle_vote_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "protect" => {
+            let msg = protect_adapter::handle_protect_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "automod" => {
+            let msg = automod_adapter::handle_automod_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "responder" => {
+            let msg = responder_adapter::handle_responder_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "alerting" => {
+            let msg = alerting_adapter::handle_alerting_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "emotes" => {
+            let msg = emotes_adapter::handle_emotes_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "session" => {
+            let msg = session_adapter::handle_session_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "language" => {
+            let msg = language_adapter::handle_language_command(args, client).await;
+            (false, Some(msg))
+        }
+
         "plugin" => {
             let msg = plugin_adapter::handle_plugin_command(args, client).await;
             (false, Some(msg))
@@ -156,7 +276,7 @@ pub async fn dispatch_grpc(
             match system::handle_system_command(args, process_manager, Some(client)).await {
                 Ok(msg) => {
                     // Check if this was a shutdown command
-                    let should_quit = args.get(0) == Some(&"shutdown") && msg.contains("Shutdown scheduled");
+                    let should_quit = args.first() == Some(&"shutdown") && msg.contains("Shutdown scheduled");
                     (should_quit, Some(msg))
                 },
                 Err(e) => (false, Some(format!("System command error: {}", e))),
@@ -173,6 +293,38 @@ pub async fn dispatch_grpc(
             (false, Some(msg))
         }
 
+        "simulate" => {
+            let msg = simulate_adapter::handle_simulate_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "test_harness" => {
+            if args.first() == Some(&"scenario") {
+                let msg = test_harness::handle_scenario_command(&args[1..], client, tui_module, process_manager).await;
+                return (false, Some(msg));
+            }
+            if args.first() == Some(&"loadtest") {
+                let msg = test_harness::handle_loadtest_command(&args[1..], client).await;
+                return (false, Some(msg));
+            }
+            // The built-in suites print their own results as they run
+            let msg = match TestHarnessCommand::execute_from_args(args).await {
+                Ok(()) => String::new(),
+                Err(e) => format!("Failed: {}", e),
+            };
+            (false, Some(msg))
+        }
+
+        "watch" => {
+            let msg = watch_adapter::handle_watch_command(args, client).await;
+            (false, Some(msg))
+        }
+
+        "alias" => {
+            let msg = alias_adapter::handle_alias_command(args, tui_module);
+            (false, Some(msg))
+        }
+
         "quit" => {
             (true, Some("(TUI) shutting down...".to_string()))
         }
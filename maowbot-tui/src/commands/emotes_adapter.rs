@@ -0,0 +1,104 @@
+// Emote usage and emote set command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::emote_sets::EmoteSetCommands, commands::emote_stats::EmoteStatsCommands};
+use maowbot_proto::maowbot::services::ListChannelEmotesResponse;
+
+/// Emotes listed when no limit is given.
+const DEFAULT_LIMIT: i32 = 15;
+
+pub async fn handle_emotes_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "top" => {
+            let (minutes, days) = match args.get(1).map(|w| parse_window(w)) {
+                None => (60, 0),
+                Some(Ok(window)) => window,
+                Some(Err(e)) => return e,
+            };
+            let channel = args.get(2).copied().unwrap_or("");
+            let limit = match args.get(3).map(|l| l.parse::<i32>()) {
+                None => DEFAULT_LIMIT,
+                Some(Ok(n)) if n > 0 => n,
+                _ => return "Limit must be a positive number.".to_string(),
+            };
+
+            match EmoteStatsCommands::top_emotes(client, channel, minutes, days, limit).await {
+                Ok(stats) if stats.emotes.is_empty() => {
+                    format!("No emotes used in #{} in {}.", stats.channel, stats.window)
+                }
+                Ok(stats) => {
+                    let mut out = format!("Top emotes in #{} in {}:\n", stats.channel, stats.window);
+                    for (i, e) in stats.emotes.iter().enumerate() {
+                        out.push_str(&format!("  {:>2}. {:<24} {:>7}  ({})\n", i + 1, e.emote_name, e.uses, e.provider));
+                    }
+                    out
+                }
+                Err(e) => format!("Error fetching emote stats => {}", e),
+            }
+        }
+
+        "sets" => {
+            let channel = args.get(1).copied().unwrap_or("");
+            match EmoteSetCommands::list_channel_emotes(client, channel, false).await {
+                Ok(sets) => format_sets(&sets),
+                Err(e) => format!("Error fetching emote sets => {}", e),
+            }
+        }
+
+        "refresh" => {
+            let channel = args.get(1).copied().unwrap_or("");
+            match EmoteSetCommands::refresh_channel_emotes(client, channel).await {
+                Ok(sets) => format!("Refetched #{}: {} channel emotes.", sets.channel, sets.emotes.len()),
+                Err(e) => format!("Error refreshing emote sets => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    "Usage:\n  emotes top [window] [channel] [limit]\n    window: 15m, 1h (default), today, 7d, ...\n  emotes sets [channel]\n  emotes refresh [channel]\n".to_string()
+}
+
+/// Per-provider counts and the emote codes of each.
+fn format_sets(sets: &ListChannelEmotesResponse) -> String {
+    if sets.emotes.is_empty() {
+        return format!("#{} has no 7TV, BTTV or FFZ emotes.", sets.channel);
+    }
+    let fetched = sets.fetched_at.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+        .map(|t| format!(" (fetched {})", t.with_timezone(&chrono::Local).format("%H:%M")))
+        .unwrap_or_default();
+    let mut out = format!("Third-party emotes of #{}{}:\n", sets.channel, fetched);
+    for provider in ["7tv", "bttv", "ffz"] {
+        let names: Vec<&str> = sets.emotes.iter()
+            .filter(|e| e.provider == provider)
+            .map(|e| e.emote_name.as_str())
+            .collect();
+        if !names.is_empty() {
+            out.push_str(&format!("  {:<5} {:>4}  {}\n", provider, names.len(), names.join(" ")));
+        }
+    }
+    out
+}
+
+/// "15m"/"1h" as rolling minutes, "today"/"7d" as days.
+fn parse_window(arg: &str) -> Result<(i32, i32), String> {
+    let arg = arg.to_lowercase();
+    let invalid = || format!("Invalid window '{}'. Use e.g. 15m, 1h, today or 7d.", arg);
+    let number = |n: &str| n.parse::<i32>().ok().filter(|n| *n > 0).ok_or_else(invalid);
+    if arg == "today" {
+        Ok((0, 1))
+    } else if let Some(n) = arg.strip_suffix('m') {
+        Ok((number(n)?, 0))
+    } else if let Some(n) = arg.strip_suffix('h') {
+        Ok((number(n)?.saturating_mul(60), 0))
+    } else if let Some(n) = arg.strip_suffix('d') {
+        Ok((0, number(n)?))
+    } else {
+        Err(invalid())
+    }
+}
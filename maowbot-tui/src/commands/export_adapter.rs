@@ -0,0 +1,95 @@
+// Data export command adapter for TUI
+use std::io::{stdout, Write};
+use std::path::Path;
+use maowbot_common_ui::{GrpcClient, commands::export::{ExportCommands, ExportFilter}};
+use tokio::io::AsyncWriteExt;
+use super::chatlog_adapter::parse_time;
+
+pub async fn handle_export_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.len() < 2 {
+        return usage();
+    }
+    let kind = args[0].to_lowercase();
+    let path = Path::new(args[1]);
+
+    let mut filter = ExportFilter { kind, ..Default::default() };
+    let mut i = 2;
+    while i < args.len() {
+        let Some(value) = args.get(i + 1) else {
+            return format!("Missing value for {}\n{}", args[i], usage());
+        };
+        match args[i] {
+            "--format" => filter.format = value.to_lowercase(),
+            "--platform" => filter.platform = value.to_string(),
+            "--channel" => filter.channel = value.to_string(),
+            "--since" => match parse_time(value) {
+                Ok(t) => filter.since = Some(t),
+                Err(e) => return e,
+            },
+            "--until" => match parse_time(value) {
+                Ok(t) => filter.until = Some(t),
+                Err(e) => return e,
+            },
+            other => return format!("Unknown option '{}'\n{}", other, usage()),
+        }
+        i += 2;
+    }
+    // Without --format, the file extension decides
+    if filter.format.is_empty() {
+        filter.format = match path.extension().and_then(|e| e.to_str()) {
+            Some("jsonl") | Some("ndjson") | Some("json") => "jsonl".to_string(),
+            _ => "csv".to_string(),
+        };
+    }
+
+    let mut chunks = match ExportCommands::export(client, filter).await {
+        Ok(stream) => stream,
+        Err(e) => return format!("Error starting export => {}", e),
+    };
+    let mut file = match tokio::fs::File::create(path).await {
+        Ok(f) => f,
+        Err(e) => return format!("Cannot create {} => {}", path.display(), e),
+    };
+
+    let mut rows = 0;
+    loop {
+        match chunks.message().await {
+            Ok(Some(chunk)) => {
+                if let Err(e) = file.write_all(&chunk.data).await {
+                    return format!("Error writing {} after {} row(s) => {}", path.display(), rows, e);
+                }
+                rows = chunk.rows_written;
+                print!("\rExporting... {}", progress(rows, chunk.total_rows));
+                let _ = stdout().flush();
+            }
+            Ok(None) => break,
+            Err(e) => {
+                println!();
+                return format!("Export stopped after {} row(s); {} is incomplete => {}", rows, path.display(), e);
+            }
+        }
+    }
+    println!();
+    if let Err(e) = file.flush().await {
+        return format!("Error writing {} => {}", path.display(), e);
+    }
+    format!("Exported {} row(s) to {}.", rows, path.display())
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  export <chat|events|commands> <file> [--format csv|jsonl] [--since T] [--until T] [--platform P] [--channel C]\n");
+    out.push_str("  T is e.g. 7d, 12h, 30m ago or YYYY-MM-DD; the default range is the last 7 days\n");
+    out
+}
+
+/// "1200/4800 rows (25%)"
+fn progress(rows: i64, total: i64) -> String {
+    // Rows stored between the count and their batch can push past it
+    let total = total.max(rows);
+    if total == 0 {
+        return "0 rows".to_string();
+    }
+    format!("{}/{} rows ({}%)", rows, total, rows * 100 / total)
+}
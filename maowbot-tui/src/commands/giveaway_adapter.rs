@@ -0,0 +1,224 @@
+// Giveaway command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::giveaway::{GiveawayCommands, NewGiveaway}};
+use maowbot_proto::maowbot::services::{Giveaway, GiveawayEntry};
+
+/// Giveaways searched when resolving a short id.
+const RECENT_GIVEAWAYS: i32 = 20;
+
+pub async fn handle_giveaway_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "open" => open(&args[1..], client).await,
+
+        "list" => match GiveawayCommands::list(client, RECENT_GIVEAWAYS).await {
+            Ok(giveaways) if giveaways.is_empty() => "No giveaways yet.".to_string(),
+            Ok(giveaways) => {
+                let mut out = String::new();
+                for g in &giveaways {
+                    out.push_str(&format_giveaway(g));
+                    out.push('\n');
+                }
+                out
+            }
+            Err(e) => format!("Error listing giveaways => {}", e),
+        },
+
+        "show" => {
+            let id = match resolve_id(args.get(1).copied(), client).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            match GiveawayCommands::get(client, &id).await {
+                Ok(details) => {
+                    let mut out = format_giveaway(&details.giveaway);
+                    out.push('\n');
+                    for entry in &details.entries {
+                        out.push_str(&format!("  {}\n", format_entry(entry)));
+                    }
+                    out
+                }
+                Err(e) => format!("Error loading giveaway => {}", e),
+            }
+        }
+
+        "close" | "cancel" => {
+            let id = match resolve_id(args.get(1).copied(), client).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let result = if args[0].eq_ignore_ascii_case("close") {
+                GiveawayCommands::close(client, &id).await
+            } else {
+                GiveawayCommands::cancel(client, &id).await
+            };
+            match result {
+                Ok(g) => format!("Giveaway \"{}\" is now {} ({} entries).", g.title, g.status, g.entry_count),
+                Err(e) => format!("Error updating giveaway => {}", e),
+            }
+        }
+
+        "draw" => {
+            let id = match resolve_id(args.get(1).copied(), client).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let count = match args.get(2).map(|c| c.parse::<i32>()) {
+                None => 1,
+                Some(Ok(n)) if n > 0 => n,
+                Some(_) => return format!("Invalid winner count '{}'", args[2]),
+            };
+            match GiveawayCommands::draw(client, &id, count).await {
+                Ok(winners) => {
+                    let mut out = String::from("Winners:\n");
+                    for w in &winners {
+                        out.push_str(&format!("  {}\n", format_entry(w)));
+                    }
+                    out
+                }
+                Err(e) => format!("Error drawing winners => {}", e),
+            }
+        }
+
+        "reroll" => {
+            let id = match resolve_id(args.get(1).copied(), client).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            match GiveawayCommands::reroll(client, &id, args.get(2).copied()).await {
+                Ok(winner) => format!("New winner: {}", format_entry(&winner)),
+                Err(e) => format!("Error re-rolling => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  giveaway open <title...> [--keyword K] [--points N] [--channel C]\n");
+    out.push_str("                [--sub-only] [--follow-days N] [--exclude-winners DAYS] [--sub-weight N]\n");
+    out.push_str("  giveaway list\n");
+    out.push_str("  giveaway show [id]                # id defaults to the latest giveaway\n");
+    out.push_str("  giveaway close [id]\n");
+    out.push_str("  giveaway cancel [id]\n");
+    out.push_str("  giveaway draw [id] [count]\n");
+    out.push_str("  giveaway reroll [id] [username]   # replaces the latest winner if no username\n");
+    out
+}
+
+async fn open(args: &[&str], client: &GrpcClient) -> String {
+    let mut new = NewGiveaway::default();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i];
+        if !flag.starts_with("--") {
+            words.push(flag);
+            i += 1;
+            continue;
+        }
+        if flag == "--sub-only" {
+            new.sub_only = true;
+            i += 1;
+            continue;
+        }
+        let Some(value) = args.get(i + 1).copied() else {
+            return format!("Missing value for {}", flag);
+        };
+        let number = |value: &str| value.parse::<i64>().ok().filter(|n| *n >= 0)
+            .ok_or_else(|| format!("Invalid number '{}' for {}", value, flag));
+        let parsed = match flag {
+            "--keyword" => { new.keyword = value.to_string(); Ok(()) }
+            "--channel" => { new.channel = value.to_string(); Ok(()) }
+            "--points" => number(value).map(|n| new.points_cost = n),
+            "--follow-days" => number(value).map(|n| new.min_follow_days = n as i32),
+            "--exclude-winners" => number(value).map(|n| new.exclude_winners_days = n as i32),
+            "--sub-weight" => number(value).map(|n| new.sub_weight = n.max(1) as i32),
+            _ => return format!("Unknown option '{}'\n{}", flag, usage()),
+        };
+        if let Err(e) = parsed {
+            return e;
+        }
+        i += 2;
+    }
+    new.title = words.join(" ");
+    if new.title.is_empty() || (new.keyword.is_empty() && new.points_cost == 0) {
+        return "A giveaway needs a title and --keyword and/or --points.".to_string();
+    }
+
+    match GiveawayCommands::open(client, new).await {
+        Ok(g) => format!("Opened {}", format_giveaway(&g)),
+        Err(e) => format!("Error opening giveaway => {}", e),
+    }
+}
+
+/// A full id, an id prefix among recent giveaways, or the latest giveaway.
+async fn resolve_id(arg: Option<&str>, client: &GrpcClient) -> Result<String, String> {
+    if let Some(id) = arg {
+        if id.len() == 36 {
+            return Ok(id.to_string());
+        }
+    }
+    let recent = GiveawayCommands::list(client, RECENT_GIVEAWAYS).await
+        .map_err(|e| format!("Error listing giveaways => {}", e))?;
+    let found = match arg {
+        None => recent.first(),
+        Some(prefix) => recent.iter().find(|g| g.giveaway_id.starts_with(prefix)),
+    };
+    found.map(|g| g.giveaway_id.clone())
+        .ok_or_else(|| format!("No giveaway matches '{}'.", arg.unwrap_or("latest")))
+}
+
+fn format_giveaway(g: &Giveaway) -> String {
+    let mut entry = Vec::new();
+    if !g.keyword.is_empty() {
+        entry.push(format!("keyword {}", g.keyword));
+    }
+    if g.points_cost > 0 {
+        entry.push(format!("{} points", g.points_cost));
+    }
+    let mut rules = Vec::new();
+    if let Some(r) = &g.rules {
+        if r.sub_only {
+            rules.push("subs only".to_string());
+        }
+        if r.min_follow_days > 0 {
+            rules.push(format!("followed {}d", r.min_follow_days));
+        }
+        if r.exclude_winners_days > 0 {
+            rules.push(format!("no winners from last {}d", r.exclude_winners_days));
+        }
+        if r.sub_weight > 1 {
+            rules.push(format!("subs x{}", r.sub_weight));
+        }
+    }
+    let mut out = format!(
+        "[{}] \"{}\" in #{} - {} - {} - {} entries",
+        &g.giveaway_id[..8.min(g.giveaway_id.len())], g.title, g.channel, g.status, entry.join(" / "), g.entry_count
+    );
+    if !rules.is_empty() {
+        out.push_str(&format!(" ({})", rules.join(", ")));
+    }
+    out
+}
+
+fn format_entry(e: &GiveawayEntry) -> String {
+    let mut out = e.username.clone();
+    if e.is_subscriber {
+        out.push_str(" (sub)");
+    }
+    if e.weight > 1 {
+        out.push_str(&format!(" x{}", e.weight));
+    }
+    if e.is_winner {
+        out.push_str(" - WINNER");
+    } else if e.rerolled {
+        out.push_str(" - re-rolled");
+    }
+    out
+}
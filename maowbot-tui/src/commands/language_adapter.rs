@@ -0,0 +1,119 @@
+// Localization command adapter for TUI
+use std::collections::HashMap;
+use maowbot_common_ui::{GrpcClient, commands::localization::LocalizationCommands};
+
+pub async fn handle_language_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "list" => {
+            let languages = match LocalizationCommands::list_languages(client).await {
+                Ok(r) => r,
+                Err(e) => return format!("Error listing languages => {}", e),
+            };
+            let mut out = format!("Default language: {}\n", languages.default_language);
+            for l in &languages.languages {
+                out.push_str(&format!(
+                    "  {:<8} {:>3}/{} translated  {:>3} custom{}\n",
+                    l.language, l.translated, l.total, l.custom_messages,
+                    if l.bundled { "  (bundled)" } else { "" },
+                ));
+            }
+            match LocalizationCommands::list_channel_languages(client).await {
+                Ok(r) if r.channels.is_empty() => out.push_str("No channels have their own language.\n"),
+                Ok(r) => {
+                    out.push_str("Channels:\n");
+                    for c in r.channels {
+                        out.push_str(&format!("  {:<12} #{:<24} {}\n", c.platform, c.channel, c.language));
+                    }
+                }
+                Err(e) => out.push_str(&format!("Error listing channel languages => {}\n", e)),
+            }
+            out
+        }
+
+        "channel" => {
+            if args.len() < 3 {
+                return "Usage: language channel <channel> <language|default> [platform]".to_string();
+            }
+            let language = match args[2].to_lowercase().as_str() {
+                "default" | "none" => String::new(),
+                other => other.to_string(),
+            };
+            let platform = args.get(3).copied().unwrap_or("twitch-irc");
+            match LocalizationCommands::set_channel_language(client, platform, args[1], &language).await {
+                Ok(()) if language.is_empty() => format!("#{} now uses the default language.", args[1].trim_start_matches('#')),
+                Ok(()) => format!("#{} now uses '{}'.", args[1].trim_start_matches('#'), language),
+                Err(e) => format!("Error setting channel language => {}", e),
+            }
+        }
+
+        "keys" => {
+            let language = args.get(1).copied().unwrap_or("");
+            let only_missing = args.get(2).is_some_and(|a| a.eq_ignore_ascii_case("missing"));
+            match LocalizationCommands::list_messages(client, language).await {
+                Ok(r) => {
+                    let mut out = format!("Messages in '{}':\n", r.language);
+                    let mut shown = 0;
+                    for m in r.messages.iter().filter(|m| !only_missing || m.source == "fallback") {
+                        out.push_str(&format!("  {} [{}]\n    {}\n", m.key, m.source, m.text));
+                        shown += 1;
+                    }
+                    if shown == 0 {
+                        out.push_str("  (none)\n");
+                    }
+                    out
+                }
+                Err(e) => format!("Error listing messages => {}", e),
+            }
+        }
+
+        "set" => {
+            if args.len() < 4 {
+                return "Usage: language set <language> <key> <template...>".to_string();
+            }
+            let messages = HashMap::from([(args[2].to_string(), args[3..].join(" "))]);
+            match LocalizationCommands::set_messages(client, args[1], messages).await {
+                Ok(_) => format!("Set '{}' in '{}'.", args[2], args[1]),
+                Err(e) => format!("Error setting message => {}", e),
+            }
+        }
+
+        "unset" => {
+            if args.len() < 3 {
+                return "Usage: language unset <language> <key>".to_string();
+            }
+            match LocalizationCommands::delete_message(client, args[1], args[2]).await {
+                Ok(true) => format!("Removed the custom '{}' in '{}'.", args[2], args[1]),
+                Ok(false) => format!("'{}' has no custom text in '{}'.", args[2], args[1]),
+                Err(e) => format!("Error removing message => {}", e),
+            }
+        }
+
+        "import" => {
+            if args.len() < 3 {
+                return "Usage: language import <language> <file.json>".to_string();
+            }
+            let contents = match std::fs::read_to_string(args[2]) {
+                Ok(c) => c,
+                Err(e) => return format!("Could not read '{}' => {}", args[2], e),
+            };
+            let messages: HashMap<String, String> = match serde_json::from_str(&contents) {
+                Ok(m) => m,
+                Err(e) => return format!("'{}' is not a JSON object of key => template: {}", args[2], e),
+            };
+            match LocalizationCommands::set_messages(client, args[1], messages).await {
+                Ok(stored) => format!("Imported {} messages into '{}'.", stored, args[1]),
+                Err(e) => format!("Error importing language pack => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    "Usage:\n  language list\n  language channel <channel> <language|default> [platform]\n  language keys [language] [missing]\n  language set <language> <key> <template...>\n  language unset <language> <key>\n  language import <language> <file.json>\n".to_string()
+}
@@ -101,7 +101,7 @@ async fn member_info(identifier: &str, bot_api: &Arc<dyn BotApi>) -> String {
                         pid.platform_roles
                     ));
                 }
-                output.push_str("\n");
+                output.push('\n');
             }
         }
         Err(e) => {
@@ -247,7 +247,7 @@ async fn member_chat(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
     } else {
         out.push_str("  limit=ALL\n");
     }
-    out.push_str("\n");
+    out.push('\n');
 
     for (i, m) in messages.iter().enumerate() {
         let n = i as i64 + 1 + offset;
@@ -302,7 +302,7 @@ async fn member_list(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
 
     // Paginated listing
     let total = all_users.len();
-    let total_pages = (total + page_size - 1) / page_size;
+    let total_pages = total.div_ceil(page_size);
     let mut out = String::new();
 
     let mut start = 0;
@@ -85,7 +85,7 @@ async fn member_info(identifier: &str, client: &GrpcClient) -> String {
                         pid.platform_roles
                     ));
                 }
-                output.push_str("\n");
+                output.push('\n');
             }
 
             // Show user analysis
@@ -148,7 +148,7 @@ async fn member_list(args: &[&str], client: &GrpcClient) -> String {
 
             // Paginated listing
             let total = all_users.len();
-            let total_pages = (total + page_size - 1) / page_size;
+            let total_pages = total.div_ceil(page_size);
             let mut out = String::new();
 
             let mut start = 0;
@@ -9,16 +9,13 @@ mod platform;
 mod plugin;
 mod twitch;
 mod user;
-mod vrchat;
 mod member;
 mod command;
 mod redeem;
-mod osc;
 mod drip;
 mod config;
 mod discord;
 mod obs;
-mod pipeline;
 pub mod system;
 pub mod test_grpc;
 pub mod user_grpc;
@@ -44,6 +41,24 @@ pub mod connection_adapter;
 pub mod unified_user_adapter;
 pub mod diagnostics_adapter;
 pub mod pipeline_adapter;
+pub mod watch_adapter;
+pub mod alias_adapter;
+pub mod simulate_adapter;
+pub mod workspace_adapter;
+pub mod token_adapter;
+pub mod audit_adapter;
+pub mod chatlog_adapter;
+pub mod export_adapter;
+pub mod giveaway_adapter;
+pub mod vote_adapter;
+pub mod protect_adapter;
+pub mod automod_adapter;
+pub mod responder_adapter;
+pub mod alerting_adapter;
+pub mod emotes_adapter;
+pub mod session_adapter;
+pub mod language_adapter;
+pub mod paging;
 mod dispatch_grpc;
 pub mod test_harness;
 pub mod simulate;
@@ -64,7 +79,7 @@ pub async fn dispatch_async(
 
     match cmd.as_str() {
         "help" => {
-            let subcmd = args.get(0).map(|s| *s).unwrap_or("");
+            let subcmd = args.first().copied().unwrap_or("");
             let msg = help::show_command_help(subcmd);
             (false, Some(msg))
         }
@@ -85,7 +100,7 @@ pub async fn dispatch_async(
         }
 
         "status" => {
-            let subcmd = args.get(0).map(|s| s.to_lowercase());
+            let subcmd = args.first().map(|s| s.to_lowercase());
             let status_data = bot_api.status().await;
 
             let mut output = format!("Uptime={}s\nConnected Plugins:\n", status_data.uptime_seconds);
@@ -181,7 +196,7 @@ pub async fn dispatch_async(
         }
 
         "connection" => {
-            let full_args = [cmd.as_str()].iter().chain(args.iter()).map(|s| *s).collect::<Vec<_>>();
+            let full_args = [cmd.as_str()].iter().chain(args.iter()).copied().collect::<Vec<_>>();
             let message = connectivity::handle_connectivity_command(&full_args[1..], bot_api, tui_module).await;
             (false, Some(message))
         }
@@ -189,7 +204,7 @@ pub async fn dispatch_async(
         // Keep legacy support for now
         "autostart" | "start" | "stop" | "chat" => {
             let message = connectivity::handle_connectivity_command(
-                &[cmd.as_str()].iter().chain(args.iter()).map(|s| *s).collect::<Vec<_>>(),
+                &[cmd.as_str()].iter().chain(args.iter()).copied().collect::<Vec<_>>(),
                 bot_api,
                 tui_module
             ).await;
@@ -1,5 +1,7 @@
 // OSC command adapter for TUI
-use maowbot_common_ui::{GrpcClient, commands::osc::OscCommands};
+use maowbot_common_ui::{GrpcClient, commands::{config::ConfigCommands, osc::OscCommands}};
+use std::collections::HashMap;
+use std::io::{stdin, stdout, Write};
 use std::sync::Arc;
 use crate::tui_module_simple::SimpleTuiModule;
 
@@ -20,6 +22,19 @@ pub async fn handle_osc_command(
     toggle list                   - Show all configured OSC toggles
     toggle test <param> <value>   - Test sending OSC parameter
     toggle active                 - Show currently active toggles
+  osc pack <subcommand>           - Share toggles and their redeems as drip packs
+    pack export <file> <name...>  - Save every toggle and redeem to a JSON pack
+    pack import <file>            - Map a pack onto the current avatar and import it
+  osc relay <subcommand>          - Relay Twitch chat to the VRChat chatbox
+    relay status                  - Show relay settings
+    relay on|off                  - Turn the relay on or off
+    relay filters <list>          - mentions, mods, highlighted (comma-separated) or all
+  osc midi <subcommand>           - Map MIDI notes/CCs to OSC, OBS scenes and chat macros
+    midi list                     - Show mappings and the open MIDI device
+    midi learn <name> <action...> - Map the next note or controller you touch
+  osc control <subcommand>        - Let chat drive parameters with !toggle and !set
+    control list                  - Show chat controls
+    control add <name> <param> <type> [opts] - Add or replace a chat control
   osc set <subcommand>            - Configure OSC destinations
     set vrcdest <ip:port>         - Set VRChat OSC destination (default: 127.0.0.1:9000)
     set robodest <ip:port>        - Set Robot OSC destination
@@ -180,7 +195,7 @@ pub async fn handle_osc_command(
                     let value = args[4];
                     let duration = args.get(5).and_then(|s| s.parse::<u64>().ok());
                     
-                    let result = match param_type {
+                    match param_type {
                         "bool" => {
                             match value.parse::<bool>() {
                                 Ok(bool_val) => {
@@ -229,9 +244,7 @@ pub async fn handle_osc_command(
                             }
                         }
                         _ => "Invalid type. Use 'bool', 'int', or 'float'.".to_string(),
-                    };
-                    
-                    result
+                    }
                 }
                 "active" => {
                     match OscCommands::list_active_toggles(client, None).await {
@@ -268,6 +281,10 @@ pub async fn handle_osc_command(
                 _ => "Unknown toggle subcommand. Use 'osc toggle' for help.".to_string(),
             }
         },
+        "pack" => handle_pack(args, client).await,
+        "relay" => handle_relay(args, client).await,
+        "midi" => handle_midi(args, client).await,
+        "control" => handle_control(args, client).await,
         "set" => {
             if args.len() < 2 {
                 return r#"Usage:
@@ -375,4 +392,413 @@ pub async fn handle_osc_command(
         },
         _ => "Unknown subcommand. Type 'osc' for help.".to_string(),
     }
-}
\ No newline at end of file
+}
+
+const RELAY_USAGE: &str = r#"Usage:
+  osc relay status                - Show relay settings
+  osc relay on|off                - Turn the chatbox relay on or off
+  osc relay filters <list>        - mentions, mods, highlighted (comma-separated) or all
+  osc relay channels <list|all>   - Only relay these Twitch channels
+  osc relay interval <seconds>    - Minimum seconds each line stays up
+  osc relay queue <n>             - Lines kept waiting before the oldest are dropped
+  osc relay names <n>             - Characters of a name shown before it's cut off"#;
+
+const RELAY_FILTERS: [&str; 4] = ["mentions", "mods", "highlighted", "all"];
+
+/// Relay settings shown by `osc relay status`, with their defaults.
+const RELAY_SETTINGS: [(&str, &str, &str); 6] = [
+    ("enabled", "osc.relay.enabled", "false"),
+    ("filters", "osc.relay.filters", "mentions,mods,highlighted"),
+    ("channels", "osc.relay.channels", ""),
+    ("interval", "osc.relay.min_interval_seconds", "3"),
+    ("queue", "osc.relay.max_queue", "5"),
+    ("names", "osc.relay.max_name_length", "12"),
+];
+
+async fn set_relay_setting(client: &GrpcClient, key: &str, value: &str) -> String {
+    match ConfigCommands::set_config(client, key, value).await {
+        Ok(_) if value.is_empty() => format!("{} cleared.", key),
+        Ok(_) => format!("{} = {}", key, value),
+        Err(e) => format!("Error setting {} => {}", key, e),
+    }
+}
+
+async fn handle_relay(args: &[&str], client: &GrpcClient) -> String {
+    let value = args.get(2..).map(|rest| rest.join(" ")).unwrap_or_default();
+    match args.get(1).copied() {
+        None | Some("status") => {
+            let mut out = String::from("Chatbox relay:");
+            for (label, key, default) in RELAY_SETTINGS {
+                let current = match ConfigCommands::get_config(client, key).await {
+                    Ok(result) => result.value,
+                    Err(_) => default.to_string(),
+                };
+                let shown = match (label, current.as_str()) {
+                    ("channels", "") => "all",
+                    (_, v) => v,
+                };
+                out.push_str(&format!("\n  {:<9} {}", label, shown));
+            }
+            out.push_str("\nThe OSC service must be running for lines to reach VRChat.");
+            out
+        }
+        Some("on") => set_relay_setting(client, "osc.relay.enabled", "true").await,
+        Some("off") => set_relay_setting(client, "osc.relay.enabled", "false").await,
+        Some("filters") if !value.is_empty() => {
+            let filters: Vec<String> = value.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|f| !f.is_empty())
+                .map(str::to_ascii_lowercase)
+                .collect();
+            if let Some(unknown) = filters.iter().find(|f| !RELAY_FILTERS.contains(&f.as_str())) {
+                return format!("Unknown filter '{}'. Expected: {}", unknown, RELAY_FILTERS.join(", "));
+            }
+            set_relay_setting(client, "osc.relay.filters", &filters.join(",")).await
+        }
+        Some("channels") if !value.is_empty() => {
+            let channels = if value.eq_ignore_ascii_case("all") {
+                String::new()
+            } else {
+                value.split(|c: char| c == ',' || c.is_whitespace())
+                    .map(|c| c.trim_start_matches('#'))
+                    .filter(|c| !c.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+            set_relay_setting(client, "osc.relay.channels", &channels).await
+        }
+        Some(sub @ ("interval" | "queue" | "names")) if value.parse::<u32>().is_ok() => {
+            let key = match sub {
+                "interval" => "osc.relay.min_interval_seconds",
+                "queue" => "osc.relay.max_queue",
+                _ => "osc.relay.max_name_length",
+            };
+            set_relay_setting(client, key, &value).await
+        }
+        _ => RELAY_USAGE.to_string(),
+    }
+}
+
+const MIDI_USAGE: &str = r#"Usage:
+  osc midi list                              - Show mappings and the open MIDI device
+  osc midi device <name|off>                 - Read MIDI from the input port named like <name>, e.g. nanoKONTROL2
+  osc midi learn <name> <action...>          - Press a key or move a control to map it
+  osc midi add <name> <note|cc> <channel> <number> <action...>
+                                             - Map a known note or controller
+  osc midi on|off <name>                     - Enable or disable a mapping
+  osc midi delete <name>                     - Remove a mapping
+Actions:
+  osc <parameter> [bool|int|float]           - Set an avatar parameter from the key or fader
+  scene <scene name...>                      - Switch OBS to a scene
+  chat <text...>                             - Send a chat macro to the broadcaster's channel"#;
+
+/// (action, target, argument) for the CreateMidiMapping request.
+fn parse_midi_action(args: &[&str]) -> Option<(String, String, String)> {
+    let (action, rest) = args.split_first()?;
+    match (*action, rest) {
+        ("osc", [parameter]) => Some(("osc".into(), parameter.to_string(), "bool".into())),
+        ("osc", [parameter, kind @ ("bool" | "int" | "float")]) => {
+            Some(("osc".into(), parameter.to_string(), kind.to_string()))
+        }
+        ("scene", scene) if !scene.is_empty() => Some(("obs_scene".into(), scene.join(" "), String::new())),
+        ("chat", text) if !text.is_empty() => Some(("chat".into(), text.join(" "), String::new())),
+        _ => None,
+    }
+}
+
+fn describe_midi_mapping(m: &maowbot_proto::maowbot::services::MidiMapping) -> String {
+    let action = match m.action.as_str() {
+        "osc" => format!("osc {} ({})", m.target, m.argument),
+        "obs_scene" if m.argument != "1" => format!("scene '{}' on OBS {}", m.target, m.argument),
+        "obs_scene" => format!("scene '{}'", m.target),
+        _ if m.argument.is_empty() => format!("chat \"{}\"", m.target),
+        _ => format!("chat \"{}\" in #{}", m.target, m.argument),
+    };
+    format!(
+        "{:<16} {:<4} {:>3} ch{:<2} => {}{}",
+        m.name, m.trigger_kind, m.number, m.channel, action,
+        if m.enabled { "" } else { " [off]" }
+    )
+}
+
+async fn handle_midi(args: &[&str], client: &GrpcClient) -> String {
+    match args.get(1).copied() {
+        None | Some("list") => match OscCommands::list_midi_mappings(client).await {
+            Ok(resp) => {
+                let mut out = if resp.device.is_empty() {
+                    "No MIDI device open (osc midi device <name>).".to_string()
+                } else {
+                    format!("Reading MIDI from {}.", resp.device)
+                };
+                if resp.mappings.is_empty() {
+                    out.push_str("\nNo MIDI mappings yet. Try 'osc midi learn <name> <action...>'.");
+                }
+                for m in &resp.mappings {
+                    out.push_str(&format!("\n  {}", describe_midi_mapping(m)));
+                }
+                out
+            }
+            Err(e) => format!("Error listing MIDI mappings: {}", e),
+        },
+        Some("device") if args.len() >= 3 => {
+            let device = args[2..].join(" ");
+            let device = if device == "off" { String::new() } else { device };
+            match ConfigCommands::set_config(client, "midi.device", &device).await {
+                Ok(_) if device.is_empty() => "MIDI input turned off.".to_string(),
+                Ok(_) => format!("Reading MIDI from {}.", device),
+                Err(e) => format!("Error setting midi.device => {}", e),
+            }
+        }
+        Some(sub @ ("learn" | "add")) => {
+            let learn = sub == "learn";
+            let (name, trigger, action_args) = if learn {
+                match args.get(2) {
+                    Some(name) => (name.to_string(), None, &args[3..]),
+                    None => return MIDI_USAGE.to_string(),
+                }
+            } else {
+                if args.len() < 6 {
+                    return MIDI_USAGE.to_string();
+                }
+                let (Ok(channel), Ok(number)) = (args[4].parse::<i32>(), args[5].parse::<i32>()) else {
+                    return MIDI_USAGE.to_string();
+                };
+                (args[2].to_string(), Some((args[3].to_string(), channel, number)), &args[6..])
+            };
+            let Some((action, target, argument)) = parse_midi_action(action_args) else {
+                return MIDI_USAGE.to_string();
+            };
+            let (trigger_kind, channel, number) = trigger.unwrap_or_default();
+            if learn {
+                println!("Press a key or move a control on your MIDI device (15s)...");
+            }
+            let request = maowbot_proto::maowbot::services::CreateMidiMappingRequest {
+                name,
+                action,
+                target,
+                argument,
+                learn,
+                learn_timeout_seconds: 15,
+                trigger_kind,
+                channel,
+                number,
+            };
+            match OscCommands::create_midi_mapping(client, request).await {
+                Ok(m) => format!("Mapped: {}", describe_midi_mapping(&m)),
+                Err(e) => format!("Error creating MIDI mapping: {}", e),
+            }
+        }
+        Some(toggle @ ("on" | "off")) if args.len() >= 3 => {
+            match OscCommands::set_midi_mapping_enabled(client, args[2], toggle == "on").await {
+                Ok(m) => describe_midi_mapping(&m),
+                Err(e) => format!("Error updating MIDI mapping: {}", e),
+            }
+        }
+        Some("delete") if args.len() >= 3 => match OscCommands::delete_midi_mapping(client, args[2]).await {
+            Ok(()) => format!("MIDI mapping '{}' deleted.", args[2]),
+            Err(e) => format!("Error deleting MIDI mapping: {}", e),
+        },
+        _ => MIDI_USAGE.to_string(),
+    }
+}
+
+const CONTROL_USAGE: &str = r#"Usage:
+  osc control list                           - Show what chat can drive with !toggle and !set
+  osc control add <name> <parameter> <bool|int|float> [--role <role>] [--min <n>] [--max <n>] [--cooldown <secs>] [--off]
+                                             - Add a chat control, or replace the one with that name
+  osc control delete <name>                  - Remove a chat control
+Roles: everyone, subscriber, vip, moderator (default), broadcaster.
+Values from chat are clamped to --min/--max, or VRChat's range when unset."#;
+
+fn describe_chat_control(c: &maowbot_proto::maowbot::services::OscChatControl) -> String {
+    let range = match (c.min_value, c.max_value) {
+        _ if c.parameter_type == "bool" => String::new(),
+        (None, None) => String::new(),
+        (min, max) => format!(
+            " {}..{}",
+            min.map(|v| v.to_string()).unwrap_or_default(),
+            max.map(|v| v.to_string()).unwrap_or_default()
+        ),
+    };
+    format!(
+        "{:<12} {} ({}{}) {}+{}{}",
+        c.name, c.parameter_name, c.parameter_type, range, c.min_role,
+        if c.cooldown_seconds > 0 { format!(", {}s cooldown", c.cooldown_seconds) } else { String::new() },
+        if c.enabled { "" } else { " [off]" }
+    )
+}
+
+async fn handle_control(args: &[&str], client: &GrpcClient) -> String {
+    match args.get(1).copied() {
+        None | Some("list") => match OscCommands::list_chat_controls(client).await {
+            Ok(controls) if controls.is_empty() => {
+                "No chat controls yet. Try 'osc control add <name> <parameter> <type>'.".to_string()
+            }
+            Ok(controls) => {
+                let mut out = "Chat controls (!toggle <name>, !set <name> <value>):".to_string();
+                for c in &controls {
+                    out.push_str(&format!("\n  {}", describe_chat_control(c)));
+                }
+                out
+            }
+            Err(e) => format!("Error listing chat controls: {}", e),
+        },
+        Some("add") if args.len() >= 5 => {
+            let mut control = maowbot_proto::maowbot::services::OscChatControl {
+                name: args[2].to_string(),
+                parameter_name: args[3].to_string(),
+                parameter_type: args[4].to_string(),
+                min_role: "moderator".to_string(),
+                enabled: true,
+                ..Default::default()
+            };
+            let mut rest = args[5..].iter();
+            while let Some(flag) = rest.next() {
+                if *flag == "--off" {
+                    control.enabled = false;
+                    continue;
+                }
+                let Some(value) = rest.next() else {
+                    return CONTROL_USAGE.to_string();
+                };
+                let parsed = match *flag {
+                    "--role" => {
+                        control.min_role = value.to_string();
+                        Ok(())
+                    }
+                    "--min" => value.parse().map(|v| control.min_value = Some(v)).map_err(|_| ()),
+                    "--max" => value.parse().map(|v| control.max_value = Some(v)).map_err(|_| ()),
+                    "--cooldown" => value.parse().map(|v| control.cooldown_seconds = v).map_err(|_| ()),
+                    _ => Err(()),
+                };
+                if parsed.is_err() {
+                    return CONTROL_USAGE.to_string();
+                }
+            }
+            match OscCommands::set_chat_control(client, control).await {
+                Ok(c) => format!("Saved: {}", describe_chat_control(&c)),
+                Err(e) => format!("Error saving chat control: {}", e),
+            }
+        }
+        Some("delete") if args.len() >= 3 => match OscCommands::delete_chat_control(client, args[2]).await {
+            Ok(()) => format!("Chat control '{}' deleted.", args[2]),
+            Err(e) => format!("Error deleting chat control: {}", e),
+        },
+        _ => CONTROL_USAGE.to_string(),
+    }
+}
+
+const PACK_USAGE: &str = "Usage: osc pack export <file.json> <name...> | osc pack import <file.json>";
+
+async fn handle_pack(args: &[&str], client: &GrpcClient) -> String {
+    match (args.get(1).copied(), args.get(2).copied()) {
+        (Some("export"), Some(path)) if args.len() >= 4 => {
+            let name = args[3..].join(" ");
+            match OscCommands::export_drip_pack(client, &name, "", "").await {
+                Ok(exported) => match std::fs::write(path, &exported.pack_json) {
+                    Ok(()) => format!("Exported {} toggle(s) as '{}' to {}.", exported.toggle_count, name, path),
+                    Err(e) => format!("Error writing {}: {}", path, e),
+                },
+                Err(e) => format!("Error exporting drip pack: {}", e),
+            }
+        }
+        (Some("import"), Some(path)) => {
+            let pack_json = match std::fs::read_to_string(path) {
+                Ok(json) => json,
+                Err(e) => return format!("Error reading {}: {}", path, e),
+            };
+            import_pack_wizard(client, &pack_json).await
+        }
+        _ => PACK_USAGE.to_string(),
+    }
+}
+
+fn prompt(question: &str) -> String {
+    print!("{}", question);
+    let _ = stdout().flush();
+    let mut answer = String::new();
+    let _ = stdin().read_line(&mut answer);
+    answer.trim().to_string()
+}
+
+/// Walks through each pack parameter, offering the avatar watcher's suggestion.
+async fn import_pack_wizard(client: &GrpcClient, pack_json: &str) -> String {
+    let preview = match OscCommands::preview_drip_pack(client, pack_json).await {
+        Ok(p) => p,
+        Err(e) => return format!("Error reading drip pack: {}", e),
+    };
+
+    println!("Drip pack '{}' => {} toggle(s)", preview.pack_name, preview.toggle_count);
+    if !preview.author.is_empty() {
+        println!("  by {}", preview.author);
+    }
+    if !preview.description.is_empty() {
+        println!("  {}", preview.description);
+    }
+    let avatar_known = !preview.avatar_id.is_empty();
+    if avatar_known {
+        println!("Current avatar: {} ({} OSC parameters)", preview.avatar_name, preview.avatar_parameters.len());
+    } else {
+        println!("No current avatar detected; parameters keep their pack names unless you rename them.");
+    }
+    println!("For each parameter press Enter to accept, type another name, or '-' to skip.\n");
+
+    let mut mapping = HashMap::new();
+    for m in &preview.matches {
+        let suggested = if avatar_known { m.avatar_parameter.clone() } else { m.pack_parameter.clone() };
+        let hint = match m.match_kind.as_str() {
+            _ if !avatar_known => String::new(),
+            "unmatched" => " (no match on this avatar)".to_string(),
+            "exact" => String::new(),
+            kind => format!(" ({})", kind.replace('_', " ")),
+        };
+        let type_warning = if avatar_known && !m.avatar_type.is_empty() && !m.avatar_type.eq_ignore_ascii_case(&m.parameter_type) {
+            format!(" [avatar type {}, pack type {}]", m.avatar_type, m.parameter_type)
+        } else {
+            String::new()
+        };
+        let answer = prompt(&format!(
+            "{} ({}) => [{}]{}{}: ",
+            m.pack_parameter,
+            m.parameter_type,
+            if suggested.is_empty() { "-" } else { suggested.as_str() },
+            hint,
+            type_warning
+        ));
+        let chosen = match answer.as_str() {
+            "" => suggested,
+            "-" => String::new(),
+            other => other.to_string(),
+        };
+        if avatar_known && !chosen.is_empty() && !preview.avatar_parameters.iter().any(|p| p.name == chosen) {
+            println!("  note: '{}' isn't a parameter of {}", chosen, preview.avatar_name);
+        }
+        mapping.insert(m.pack_parameter.clone(), chosen);
+    }
+
+    let mapped = mapping.values().filter(|v| !v.is_empty()).count();
+    if mapped == 0 {
+        return "Nothing mapped; import cancelled.".to_string();
+    }
+    if !prompt(&format!("\nImport toggles for {} parameter(s)? (y/n) ", mapped)).eq_ignore_ascii_case("y") {
+        return "Import cancelled.".to_string();
+    }
+
+    match OscCommands::import_drip_pack(client, pack_json, mapping).await {
+        Ok(results) => {
+            let imported = results.iter().filter(|r| r.status == "imported").count();
+            let mut out = format!("Imported {} of {} toggle(s).", imported, results.len());
+            for r in &results {
+                let redeem = if r.created_redeem { "new redeem" } else { "existing redeem" };
+                out.push_str(&format!("\n  {:<8} {} => {}", r.status, r.reward_name, r.parameter_name));
+                if r.status == "imported" {
+                    out.push_str(&format!(" ({})", redeem));
+                }
+                if !r.detail.is_empty() {
+                    out.push_str(&format!(" - {}", r.detail));
+                }
+            }
+            out
+        }
+        Err(e) => format!("Error importing drip pack: {}", e),
+    }
+}
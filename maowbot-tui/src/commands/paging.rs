@@ -0,0 +1,106 @@
+// Shared `--limit N` / `--offset N` handling for listing commands
+
+/// Window into a listing, parsed from `--limit N` and `--offset N` flags.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PageArgs {
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl PageArgs {
+    /// Split the paging flags out of `args`, returning them with the remaining arguments.
+    pub fn extract<'a>(args: &[&'a str]) -> Result<(PageArgs, Vec<&'a str>), String> {
+        let mut page = PageArgs::default();
+        let mut rest = Vec::with_capacity(args.len());
+        let mut i = 0;
+        while i < args.len() {
+            let (flag, inline_value) = match args[i].split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None => (args[i], None),
+            };
+            match flag {
+                "--limit" | "--offset" => {
+                    let value = match inline_value {
+                        Some(v) => v,
+                        None => {
+                            i += 1;
+                            args.get(i).copied().unwrap_or("")
+                        }
+                    };
+                    let n = value
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid value for {}: '{}'", flag, value))?;
+                    if flag == "--limit" {
+                        if n == 0 {
+                            return Err("--limit must be at least 1".to_string());
+                        }
+                        page.limit = Some(n);
+                    } else {
+                        page.offset = n;
+                    }
+                }
+                _ => rest.push(args[i]),
+            }
+            i += 1;
+        }
+        Ok((page, rest))
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.offset > 0 || self.limit.is_some()
+    }
+
+    /// How many items must be fetched for this window to be complete.
+    pub fn fetch_size(&self, default: usize) -> usize {
+        self.offset + self.limit.unwrap_or(default)
+    }
+
+    /// Keep only the items inside the window.
+    pub fn apply<T>(&self, items: Vec<T>) -> Vec<T> {
+        let iter = items.into_iter().skip(self.offset);
+        match self.limit {
+            Some(limit) => iter.take(limit).collect(),
+            None => iter.collect(),
+        }
+    }
+
+    /// Summary line for a windowed listing, or `None` when no flags were given.
+    pub fn footer(&self, shown: usize, total: usize) -> Option<String> {
+        if !self.is_set() {
+            return None;
+        }
+        if shown == 0 {
+            return Some(format!("(no items at offset {}; {} total)", self.offset, total));
+        }
+        let mut line = format!("(showing {}-{} of {}", self.offset + 1, self.offset + shown, total);
+        if self.offset + shown < total {
+            line.push_str(&format!("; next: --offset {}", self.offset + shown));
+        }
+        line.push(')');
+        Some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_flags() {
+        let (page, rest) = PageArgs::extract(&["list", "--limit", "5", "all", "--offset=10"]).unwrap();
+        assert_eq!(page, PageArgs { offset: 10, limit: Some(5) });
+        assert_eq!(rest, vec!["list", "all"]);
+
+        assert!(PageArgs::extract(&["--limit", "zero"]).is_err());
+        assert!(PageArgs::extract(&["--limit", "0"]).is_err());
+        assert!(PageArgs::extract(&["--offset"]).is_err());
+    }
+
+    #[test]
+    fn test_apply_and_footer() {
+        let page = PageArgs { offset: 2, limit: Some(3) };
+        assert_eq!(page.apply((1..=10).collect::<Vec<_>>()), vec![3, 4, 5]);
+        assert_eq!(page.footer(3, 10).unwrap(), "(showing 3-5 of 10; next: --offset 5)");
+        assert_eq!(PageArgs::default().footer(10, 10), None);
+    }
+}
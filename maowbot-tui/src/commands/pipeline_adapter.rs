@@ -1,14 +1,21 @@
 // Pipeline command adapter for TUI
-use maowbot_common_ui::{GrpcClient, commands::pipeline::PipelineCommands};
+use maowbot_common_ui::{GrpcClient, commands::pipeline::{PipelineCommands, PipelineSpec, apply_throttle_setting}};
+use maowbot_proto::maowbot::services::event_pipeline::{ExecutionLog, PipelineAction, PipelineThrottle};
 use std::io::{stdin, stdout, Write};
+use std::path::Path;
+use super::paging::PageArgs;
 
 pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
-        return "Usage: pipeline <list|create|delete|toggle|show|filter|action|history|reload>".to_string();
+        return "Usage: pipeline <list|create|import|delete|toggle|enable|disable|show|test|throttle|filter|action|history|errors|reload>".to_string();
     }
 
     match args[0] {
         "list" => {
+            let (page, args) = match PageArgs::extract(args) {
+                Ok(parsed) => parsed,
+                Err(e) => return format!("Error: {}", e),
+            };
             let include_disabled = args.get(1).map(|s| s == &"all").unwrap_or(false);
             
             match PipelineCommands::list_pipelines(client, include_disabled).await {
@@ -16,12 +23,14 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                     if result.data.pipelines.is_empty() {
                         "No pipelines found.\n".to_string()
                     } else {
+                        let total = result.data.pipelines.len();
+                        let pipelines = page.apply(result.data.pipelines);
                         let mut out = String::new();
                         out.push_str("Event Pipelines:\n");
                         out.push_str("ID                                   | Name                | Priority | Enabled | Executions\n");
                         out.push_str("-------------------------------------|---------------------|----------|---------|------------\n");
                         
-                        for pipeline in &result.data.pipelines {
+                        for pipeline in &pipelines {
                             out.push_str(&format!(
                                 "{:36} | {:19} | {:8} | {:7} | {:>6} (S:{})\n",
                                 truncate(&pipeline.pipeline_id, 36),
@@ -32,6 +41,9 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                                 pipeline.success_count,
                             ));
                         }
+                        if let Some(footer) = page.footer(pipelines.len(), total) {
+                            out.push_str(&format!("{}\n", footer));
+                        }
                         out
                     }
                 }
@@ -78,10 +90,14 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
         
         "delete" => {
             if args.len() < 2 {
-                return "Usage: pipeline delete <id>".to_string();
+                return "Usage: pipeline delete <name|id>".to_string();
             }
             
-            let pipeline_id = args[1];
+            let pipeline_id = match resolve(client, args[1]).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let pipeline_id = pipeline_id.as_str();
             
             // Confirm deletion
             println!("Are you sure you want to delete pipeline {}? (y/n): ", pipeline_id);
@@ -103,38 +119,155 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
         
         "toggle" => {
             if args.len() < 3 {
-                return "Usage: pipeline toggle <id> <enabled|disabled>".to_string();
+                return "Usage: pipeline toggle <name|id> <enabled|disabled>".to_string();
             }
             
-            let pipeline_id = args[1];
             let enabled = match args[2] {
                 "enabled" | "enable" | "on" => true,
                 "disabled" | "disable" | "off" => false,
                 _ => return "Invalid toggle state. Use 'enabled' or 'disabled'.".to_string(),
             };
             
-            match PipelineCommands::toggle_pipeline(client, pipeline_id, enabled).await {
-                Ok(_) => format!(
-                    "Pipeline {} {}.",
-                    pipeline_id,
-                    if enabled { "enabled" } else { "disabled" }
-                ),
-                Err(e) => format!("Error toggling pipeline: {}", e),
+            set_enabled(client, args[1], enabled).await
+        }
+        
+        "enable" | "disable" => {
+            if args.len() < 2 {
+                return format!("Usage: pipeline {} <name|id>", args[0]);
+            }
+            
+            set_enabled(client, args[1], args[0] == "enable").await
+        }
+        
+        "import" => {
+            if args.len() < 2 {
+                return "Usage: pipeline import <file.toml|file.json>".to_string();
+            }
+            
+            let spec = match PipelineSpec::from_file(Path::new(args[1])) {
+                Ok(spec) => spec,
+                Err(e) => return format!("Error loading pipeline spec: {}", e),
+            };
+            
+            match PipelineCommands::create_from_spec(client, &spec).await {
+                Ok(result) => {
+                    let mut out = format!(
+                        "Created pipeline '{}' (ID: {}) with {} filter(s) and {} action(s).",
+                        result.data.pipeline.name,
+                        result.data.pipeline.pipeline_id,
+                        result.data.filters_added,
+                        result.data.actions_added,
+                    );
+                    if !result.data.pipeline.enabled {
+                        out.push_str(" Pipeline is disabled.");
+                    }
+                    for warning in &result.warnings {
+                        out.push_str(&format!("\n  Warning: {}", warning));
+                    }
+                    out
+                }
+                Err(e) => format!("Error importing pipeline: {}", e),
+            }
+        }
+        
+        "test" => {
+            if args.len() < 3 {
+                return "Usage: pipeline test <name|id> <event_type> [event_json]".to_string();
+            }
+            
+            let pipeline_id = match resolve(client, args[1]).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let event_type = args[2];
+            let event_data = if args.len() > 3 { args[3..].join(" ") } else { String::new() };
+            
+            match PipelineCommands::test_fire_pipeline(client, &pipeline_id, event_type, &event_data).await {
+                Ok(result) => {
+                    let mut out = String::new();
+                    out.push_str(&format!("{}\n", result.data.message));
+                    out.push_str(&format!("  Filters passed: {}\n", if result.data.filters_passed { "Yes" } else { "No" }));
+                    if let Some(exec) = &result.data.execution {
+                        out.push_str(&format_execution(exec));
+                    }
+                    out
+                }
+                Err(e) => format!("Error test-firing pipeline: {}", e),
+            }
+        }
+        
+        "throttle" => {
+            if args.len() < 2 {
+                return "Usage: pipeline throttle <name|id> [show|set <key=value>...|clear|reset]".to_string();
+            }
+            
+            let pipeline_id = match resolve(client, args[1]).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            
+            match args.get(2).copied().unwrap_or("show") {
+                "show" => match PipelineCommands::get_pipeline_throttle(client, &pipeline_id).await {
+                    Ok(result) => format_throttle(args[1], &result.data.throttle),
+                    Err(e) => format!("Error getting throttle: {}", e),
+                },
+                "set" => {
+                    if args.len() < 4 {
+                        return "Usage: pipeline throttle <name|id> set <cooldown|user_cooldown|max|max_per_user|window>=<value>...".to_string();
+                    }
+                    // Settings not given keep their current value
+                    let mut throttle = match PipelineCommands::get_pipeline_throttle(client, &pipeline_id).await {
+                        Ok(result) => result.data.throttle,
+                        Err(e) => return format!("Error getting throttle: {}", e),
+                    };
+                    for setting in &args[3..] {
+                        if let Err(e) = apply_throttle_setting(&mut throttle, setting) {
+                            return e.to_string();
+                        }
+                    }
+                    // A run limit without a window would be rejected; a minute is the usual choice
+                    if (throttle.max_executions > 0 || throttle.max_executions_per_user > 0) && throttle.window_seconds == 0 {
+                        throttle.window_seconds = 60;
+                    }
+                    match PipelineCommands::set_pipeline_throttle(client, throttle).await {
+                        Ok(result) => format!("{}\n{}", result.data.message, format_throttle(args[1], &result.data.throttle)),
+                        Err(e) => format!("Error setting throttle: {}", e),
+                    }
+                }
+                "clear" => {
+                    let throttle = PipelineThrottle { pipeline_id, ..Default::default() };
+                    match PipelineCommands::set_pipeline_throttle(client, throttle).await {
+                        Ok(_) => format!("Throttle removed from pipeline {}.", args[1]),
+                        Err(e) => format!("Error clearing throttle: {}", e),
+                    }
+                }
+                "reset" => match PipelineCommands::reset_pipeline_throttle(client, &pipeline_id).await {
+                    Ok(result) => format!(
+                        "Throttle counters for {} reset ({} recorded runs cleared).",
+                        args[1], result.data.hits_cleared
+                    ),
+                    Err(e) => format!("Error resetting throttle: {}", e),
+                },
+                _ => "Usage: pipeline throttle <name|id> [show|set <key=value>...|clear|reset]".to_string(),
             }
         }
         
         "show" => {
             if args.len() < 2 {
-                return "Usage: pipeline show <id>".to_string();
+                return "Usage: pipeline show <name|id>".to_string();
             }
             
-            let pipeline_id = args[1];
+            let pipeline_id = match resolve(client, args[1]).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let pipeline_id = pipeline_id.as_str();
             match PipelineCommands::get_pipeline(client, pipeline_id).await {
                 Ok(result) => {
                     let pipeline = &result.data.pipeline;
                     let mut out = String::new();
                     
-                    out.push_str(&format!("Pipeline Details:\n"));
+                    out.push_str("Pipeline Details:\n");
                     out.push_str(&format!("  ID: {}\n", pipeline.pipeline_id));
                     out.push_str(&format!("  Name: {}\n", pipeline.name));
                     out.push_str(&format!("  Description: {}\n", pipeline.description));
@@ -204,10 +337,14 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
             match args[1] {
                 "add" => {
                     if args.len() < 4 {
-                        return "Usage: pipeline filter add <pipeline_id> <filter_type> [config_json] [order_index] [negated] [required]".to_string();
+                        return "Usage: pipeline filter add <pipeline> <filter_type> [config_json] [order_index] [negated] [required]".to_string();
                     }
                     
-                    let pipeline_id = args[2];
+                    let pipeline_id = match resolve(client, args[2]).await {
+                        Ok(id) => id,
+                        Err(e) => return e,
+                    };
+                    let pipeline_id = pipeline_id.as_str();
                     let filter_type = args[3];
                     let filter_config = args.get(4).unwrap_or(&"{}");
                     let filter_order = args.get(5)
@@ -254,10 +391,14 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                 
                 "list" => {
                     if args.len() < 3 {
-                        return "Usage: pipeline filter list <pipeline_id>".to_string();
+                        return "Usage: pipeline filter list <pipeline>".to_string();
                     }
                     
-                    let pipeline_id = args[2];
+                    let pipeline_id = match resolve(client, args[2]).await {
+                        Ok(id) => id,
+                        Err(e) => return e,
+                    };
+                    let pipeline_id = pipeline_id.as_str();
                     match PipelineCommands::list_filters(client, pipeline_id).await {
                         Ok(result) => {
                             if result.data.filters.is_empty() {
@@ -307,16 +448,20 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
         
         "action" => {
             if args.len() < 2 {
-                return "Usage: pipeline action <add|remove|list|types>".to_string();
+                return "Usage: pipeline action <add|condition|remove|list|types>".to_string();
             }
             
             match args[1] {
                 "add" => {
                     if args.len() < 4 {
-                        return "Usage: pipeline action add <pipeline_id> <action_type> [config_json] [order_index] [continue_on_error] [is_async] [timeout_ms] [retry_count] [retry_delay_ms]".to_string();
+                        return "Usage: pipeline action add <pipeline> <action_type> [config_json] [order_index] [continue_on_error] [is_async] [timeout_ms] [retry_count] [retry_delay_ms]".to_string();
                     }
                     
-                    let pipeline_id = args[2];
+                    let pipeline_id = match resolve(client, args[2]).await {
+                        Ok(id) => id,
+                        Err(e) => return e,
+                    };
+                    let pipeline_id = pipeline_id.as_str();
                     let action_type = args[3];
                     let action_config = args.get(4).unwrap_or(&"{}");
                     let action_order = args.get(5)
@@ -347,6 +492,7 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                         timeout_ms,
                         retry_count,
                         retry_delay_ms,
+                        None,
                     ).await {
                         Ok(result) => {
                             format!(
@@ -360,6 +506,22 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                     }
                 }
                 
+                "condition" => {
+                    if args.len() < 4 {
+                        return "Usage: pipeline action condition <action_id> <expression|previous_success|previous_failure|none>".to_string();
+                    }
+                    
+                    let action_id = args[2];
+                    let condition = args[3..].join(" ");
+                    match PipelineCommands::update_action(
+                        client, action_id, None, None, None, None, None, None, None, Some(&condition),
+                    ).await {
+                        Ok(_) if condition.trim() == "none" => format!("Action {} now always runs.", action_id),
+                        Ok(_) => format!("Action {} now runs only if: {}", action_id, condition),
+                        Err(e) => format!("Error setting condition: {}", e),
+                    }
+                }
+                
                 "remove" => {
                     if args.len() < 3 {
                         return "Usage: pipeline action remove <action_id>".to_string();
@@ -374,10 +536,14 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                 
                 "list" => {
                     if args.len() < 3 {
-                        return "Usage: pipeline action list <pipeline_id>".to_string();
+                        return "Usage: pipeline action list <pipeline>".to_string();
                     }
                     
-                    let pipeline_id = args[2];
+                    let pipeline_id = match resolve(client, args[2]).await {
+                        Ok(id) => id,
+                        Err(e) => return e,
+                    };
+                    let pipeline_id = pipeline_id.as_str();
                     match PipelineCommands::list_actions(client, pipeline_id).await {
                         Ok(result) => {
                             if result.data.actions.is_empty() {
@@ -395,6 +561,9 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                                         if action.is_async { "Yes" } else { "No" },
                                         truncate(&action.action_config, 30)
                                     ));
+                                    if let Some(condition) = describe_condition(action) {
+                                        out.push_str(&format!("      only if: {}\n", condition));
+                                    }
                                 }
                                 out
                             }
@@ -422,22 +591,62 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                     }
                 }
                 
-                _ => "Usage: pipeline action <add|remove|list|types>".to_string(),
+                _ => "Usage: pipeline action <add|condition|remove|list|types>".to_string(),
             }
         }
         
-        "history" => {
-            let pipeline_id = args.get(1).map(|s| s.to_string());
-            let limit = args.get(2)
-                .and_then(|s| s.parse::<i32>().ok())
+        "history" | "errors" => {
+            let (page, args) = match PageArgs::extract(args) {
+                Ok(parsed) => parsed,
+                Err(e) => return format!("Error: {}", e),
+            };
+            let args = args.as_slice();
+            let errors_only = args[0] == "errors";
+            let pipeline_id = match args.get(1) {
+                Some(arg) if arg.parse::<i32>().is_err() => match resolve(client, arg).await {
+                    Ok(id) => Some(id),
+                    Err(e) => return e,
+                },
+                _ => None,
+            };
+            // The pipeline argument is optional, so numeric arguments shift left without it
+            let rest = if pipeline_id.is_some() { &args[2.min(args.len())..] } else { &args[1.min(args.len())..] };
+            let limit = page.limit.map(|n| n as i32)
+                .or_else(|| rest.first().and_then(|s| s.parse::<i32>().ok()))
                 .or(Some(20));
-            let offset = args.get(3)
-                .and_then(|s| s.parse::<i32>().ok());
+            let offset = if page.offset > 0 {
+                Some(page.offset as i32)
+            } else {
+                rest.get(1).and_then(|s| s.parse::<i32>().ok())
+            };
             
-            match PipelineCommands::get_execution_history(client, pipeline_id.as_deref(), limit, offset).await {
+            match PipelineCommands::get_execution_history(client, pipeline_id.as_deref(), limit, offset, errors_only).await {
                 Ok(result) => {
                     if result.data.executions.is_empty() {
-                        "No execution history found.".to_string()
+                        if errors_only {
+                            "No failed executions found.".to_string()
+                        } else {
+                            "No execution history found.".to_string()
+                        }
+                    } else if errors_only {
+                        let mut out = String::new();
+                        out.push_str(&format!("Failed Executions ({}):\n", result.data.executions.len()));
+                        for exec in &result.data.executions {
+                            out.push_str(&format!(
+                                "\n{} | {} | {} | {}\n",
+                                exec.started_at,
+                                exec.pipeline_name,
+                                exec.event_type,
+                                exec.execution_id
+                            ));
+                            if !exec.error_message.is_empty() {
+                                out.push_str(&format!("  Error: {}\n", exec.error_message));
+                            }
+                            for action in exec.action_results.iter().filter(|a| a.status != "Success") {
+                                out.push_str(&format!("  Action {} failed: {}\n", action.action_type, action.error));
+                            }
+                        }
+                        out
                     } else {
                         let mut out = String::new();
                         out.push_str(&format!(
@@ -458,6 +667,10 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
                                 truncate(&exec.started_at, 20)
                             ));
                         }
+                        let total = (result.data.total_count.max(0) as usize).max(result.data.executions.len());
+                        if let Some(footer) = page.footer(result.data.executions.len(), total) {
+                            out.push_str(&format!("{}\n", footer));
+                        }
                         out
                     }
                 }
@@ -474,8 +687,80 @@ pub async fn handle_pipeline_command(args: &[&str], client: &GrpcClient) -> Stri
             }
         }
         
-        _ => "Usage: pipeline <list|create|delete|toggle|show|filter|action|history|reload>".to_string(),
+        _ => "Usage: pipeline <list|create|import|delete|toggle|enable|disable|show|test|throttle|filter|action|history|errors|reload>".to_string(),
+    }
+}
+
+/// Resolve a pipeline name or UUID, formatting failures for display.
+async fn resolve(client: &GrpcClient, name_or_id: &str) -> Result<String, String> {
+    PipelineCommands::resolve_pipeline_id(client, name_or_id)
+        .await
+        .map_err(|e| format!("Error resolving pipeline '{}': {}", name_or_id, e))
+}
+
+async fn set_enabled(client: &GrpcClient, name_or_id: &str, enabled: bool) -> String {
+    let pipeline_id = match resolve(client, name_or_id).await {
+        Ok(id) => id,
+        Err(e) => return e,
+    };
+    
+    match PipelineCommands::toggle_pipeline(client, &pipeline_id, enabled).await {
+        Ok(_) => format!(
+            "Pipeline {} {}.",
+            name_or_id,
+            if enabled { "enabled" } else { "disabled" }
+        ),
+        Err(e) => format!("Error toggling pipeline: {}", e),
+    }
+}
+
+fn format_execution(exec: &ExecutionLog) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("  Execution: {} ({})\n", exec.execution_id, exec.status));
+    if !exec.error_message.is_empty() {
+        out.push_str(&format!("  Error: {}\n", exec.error_message));
     }
+    for action in &exec.action_results {
+        out.push_str(&format!(
+            "  - {} [{}]{}\n",
+            action.action_type,
+            action.status,
+            if action.error.is_empty() { String::new() } else { format!(": {}", action.error) }
+        ));
+    }
+    out
+}
+
+fn format_throttle(name: &str, throttle: &PipelineThrottle) -> String {
+    let limit = |value: i32, unit: &str| {
+        if value > 0 { format!("{}{}", value, unit) } else { "off".to_string() }
+    };
+    let window = throttle.window_seconds;
+    format!(
+        "Throttle for {}:\n  Cooldown:          {}\n  User cooldown:     {}\n  Max runs:          {}\n  Max runs per user: {}\n",
+        name,
+        limit(throttle.cooldown_seconds, "s"),
+        limit(throttle.user_cooldown_seconds, "s"),
+        limit(throttle.max_executions, &format!(" per {}s", window)),
+        limit(throttle.max_executions_per_user, &format!(" per {}s", window)),
+    )
+}
+
+/// An action's condition as it would be typed, e.g. "bits >= 1000".
+fn describe_condition(action: &PipelineAction) -> Option<String> {
+    let kind = action.condition_type.as_deref().filter(|k| !k.is_empty())?;
+    let config: Option<serde_json::Value> = action.condition_config.as_deref()
+        .and_then(|c| serde_json::from_str(c).ok());
+    let field = match kind {
+        "expression" => "if",
+        "data_check" => "key",
+        _ => return Some(kind.to_string()),
+    };
+    Some(config.as_ref()
+        .and_then(|c| c.get(field))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| kind.to_string()))
 }
 
 fn truncate(s: &str, max_len: usize) -> String {
@@ -80,7 +80,7 @@ async fn handle_platform_add(plat: Platform, bot_api: &Arc<dyn BotApi>) -> Strin
             .create_platform_config(plat.clone(), vrchat_default_key, None)
             .await;
         return match res {
-            Ok(_) => format!("VRChat platform config upserted with default API key."),
+            Ok(_) => "VRChat platform config upserted with default API key.".to_string(),
             Err(e) => format!("Error storing VRChat config => {e}"),
         };
     }
@@ -88,7 +88,7 @@ async fn handle_platform_add(plat: i32, client: &GrpcClient) -> String {
             None,
             vec![]
         ).await {
-            Ok(_) => format!("VRChat platform config created with default API key."),
+            Ok(_) => "VRChat platform config created with default API key.".to_string(),
             Err(e) => format!("Error storing VRChat config => {}", e),
         }
     } else {
@@ -101,8 +101,12 @@ async fn handle_platform_add(plat: i32, client: &GrpcClient) -> String {
             6 => Some("https://dev.twitch.tv/console"), // PLATFORM_TWITCH_HELIX
             3 => Some("https://discord.com/developers/applications"), // PLATFORM_DISCORD
             4 => Some("https://dashboard.vrchat.com/"), // PLATFORM_VRCHAT
+            8 => Some("https://kick.com/settings/developer"), // PLATFORM_KICK
             _ => None,
         };
+        if plat == 8 {
+            println!("Kick apps must list http://localhost:9876/callback as a redirect URL.");
+        }
         
         if let Some(url) = dev_console_url {
             println!("Open the dev console for {} now? (y/n):", platform_str);
@@ -270,6 +274,7 @@ fn parse_platform(s: &str) -> Result<i32, String> {
         "discord" => 3, // PLATFORM_DISCORD
         "vrchat" => 4, // PLATFORM_VRCHAT
         "vrchat-pipeline" | "vrchatpipeline" => 5, // PLATFORM_VRCHAT_PIPELINE
+        "kick" => 8, // PLATFORM_KICK
         _ => return Err(format!("Unknown platform: {}", s)),
     };
     Ok(plat)
@@ -284,6 +289,7 @@ fn format_platform(plat: i32) -> String {
         3 => "Discord",
         4 => "VRChat",
         5 => "VRChatPipeline",
+        8 => "Kick",
         _ => "Unknown",
     }.to_string()
 }
@@ -297,6 +303,7 @@ fn format_platform_str(plat: &str) -> String {
         "3" => "Discord",
         "4" => "VRChat",
         "5" => "VRChatPipeline",
+        "8" => "Kick",
         _ => plat,
     }.to_string()
 }
\ No newline at end of file
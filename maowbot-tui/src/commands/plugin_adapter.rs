@@ -68,7 +68,7 @@ pub async fn handle_status_command(args: &[&str], client: &GrpcClient) -> String
             }
             
             // If "config" subcommand is provided, also show config
-            let subcmd = args.get(0).map(|s| s.to_lowercase());
+            let subcmd = args.first().map(|s| s.to_lowercase());
             if subcmd.as_deref() == Some("config") {
                 // Import config commands
                 use maowbot_common_ui::commands::config::ConfigCommands;
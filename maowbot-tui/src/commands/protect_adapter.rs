@@ -0,0 +1,157 @@
+// Raid defense command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::protection::ProtectionCommands};
+use maowbot_proto::maowbot::services::ProtectionIncident;
+
+/// Incidents listed, and searched when resolving a short id.
+const RECENT_INCIDENTS: i32 = 20;
+/// Captured messages shown by "incident"; the rest are summarized.
+const SHOWN_MESSAGES: usize = 50;
+
+pub async fn handle_protect_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "status" => match ProtectionCommands::status(client).await {
+            Ok(status) => {
+                let mut out = String::new();
+                out.push_str(&format!(
+                    "Channel:         {}\n",
+                    if status.channel.is_empty() { "(no broadcaster account)".to_string() } else { format!("#{}", status.channel) }
+                ));
+                out.push_str(&format!(
+                    "Auto-detection:  {}\n",
+                    if status.auto_enabled { "on" } else { "off (config set protection.enabled true)" }
+                ));
+                out.push_str(&format!(
+                    "Shield Mode:     {}\n",
+                    match (status.shield_mode_known, status.shield_mode) {
+                        (false, _) => "unknown",
+                        (true, true) => "on",
+                        (true, false) => "off",
+                    }
+                ));
+                out.push_str(&format!("Recent messages: {}\n", status.window_messages));
+                match &status.active_incident {
+                    Some(incident) => out.push_str(&format!("Lockdown:        {}\n", format_incident(incident))),
+                    None => out.push_str("Lockdown:        none\n"),
+                }
+                out
+            }
+            Err(e) => format!("Error fetching protection status => {}", e),
+        },
+
+        "shield" => {
+            let active = match args.get(1).map(|a| a.to_lowercase()) {
+                Some(a) if a == "on" => true,
+                Some(a) if a == "off" => false,
+                _ => return "Usage: protect shield <on|off>".to_string(),
+            };
+            match ProtectionCommands::set_shield_mode(client, active).await {
+                Ok(()) => format!("Shield Mode is now {}.", if active { "on" } else { "off" }),
+                Err(e) => format!("Error setting Shield Mode => {}", e),
+            }
+        }
+
+        "lockdown" => match ProtectionCommands::start_lockdown(client, &args[1..].join(" ")).await {
+            Ok(incident) => format!("Chat locked down: {}", format_incident(&incident)),
+            Err(e) => format!("Error locking down chat => {}", e),
+        },
+
+        "lift" => match ProtectionCommands::end_lockdown(client).await {
+            Ok(incident) => format!(
+                "Lockdown lifted after {} captured message(s). Details: protect incident {}",
+                incident.messages.len(), short_id(&incident.incident_id)
+            ),
+            Err(e) => format!("Error lifting lockdown => {}", e),
+        },
+
+        "incidents" => match ProtectionCommands::list_incidents(client, RECENT_INCIDENTS).await {
+            Ok(incidents) if incidents.is_empty() => "No incidents recorded.".to_string(),
+            Ok(incidents) => {
+                let mut out = String::new();
+                for incident in &incidents {
+                    out.push_str(&format_incident(incident));
+                    out.push('\n');
+                }
+                out
+            }
+            Err(e) => format!("Error listing incidents => {}", e),
+        },
+
+        "incident" => {
+            let Some(prefix) = args.get(1) else {
+                return "Usage: protect incident <id>".to_string();
+            };
+            let id = match resolve_id(prefix, client).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            match ProtectionCommands::get_incident(client, &id).await {
+                Ok(incident) => {
+                    let mut out = format_incident(&incident);
+                    out.push_str(&format!("\nCaptured messages: {}\n", incident.messages.len()));
+                    for m in incident.messages.iter().take(SHOWN_MESSAGES) {
+                        let when = m.sent_at.as_ref()
+                            .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+                            .map(|t| t.format("%H:%M:%S").to_string())
+                            .unwrap_or_default();
+                        out.push_str(&format!("  {} {}: {}\n", when, m.username, m.text));
+                    }
+                    if incident.messages.len() > SHOWN_MESSAGES {
+                        out.push_str(&format!("  ... {} more\n", incident.messages.len() - SHOWN_MESSAGES));
+                    }
+                    out
+                }
+                Err(e) => format!("Error loading incident => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  protect status\n");
+    out.push_str("  protect shield <on|off>\n");
+    out.push_str("  protect lockdown [reason...]\n");
+    out.push_str("  protect lift\n");
+    out.push_str("  protect incidents\n");
+    out.push_str("  protect incident <id>\n");
+    out
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
+
+/// A full id or an id prefix among recent incidents.
+async fn resolve_id(arg: &str, client: &GrpcClient) -> Result<String, String> {
+    if arg.len() == 36 {
+        return Ok(arg.to_string());
+    }
+    let recent = ProtectionCommands::list_incidents(client, RECENT_INCIDENTS).await
+        .map_err(|e| format!("Error listing incidents => {}", e))?;
+    recent.iter()
+        .find(|i| i.incident_id.starts_with(arg))
+        .map(|i| i.incident_id.clone())
+        .ok_or_else(|| format!("No incident matches '{}'.", arg))
+}
+
+fn format_incident(i: &ProtectionIncident) -> String {
+    let when = |ts: &Option<prost_types::Timestamp>| ts.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
+    let ended = match when(&i.ended_at) {
+        Some(at) => format!("ended {} by {}", at, i.ended_by),
+        None => "ongoing".to_string(),
+    };
+    let actions = if i.actions.is_empty() { "no changes".to_string() } else { i.actions.join(", ") };
+    format!(
+        "[{}] {} #{} {}: {} - {} - {}",
+        short_id(&i.incident_id), when(&i.started_at).unwrap_or_default(), i.channel, i.trigger, i.details, actions, ended
+    )
+}
@@ -33,7 +33,7 @@ pub async fn handle_redeem_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
 
                     // Partition: *web_app* => is_managed=false, *internally* => is_managed=true
                     let (web_app, internal): (Vec<_>, Vec<_>) =
-                        list.into_iter().partition(|rd| rd.is_managed == false);
+                        list.into_iter().partition(|rd| !rd.is_managed);
 
                     // Try to parse associated Twitch accounts from "autostart"
                     let mut all_accounts = Vec::new();
@@ -57,7 +57,7 @@ pub async fn handle_redeem_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
                     } else {
                         output.push_str("[Web-app managed redeems]\n");
                         output.push_str(&format_table(&web_app));
-                        output.push_str("\n");
+                        output.push('\n');
                     }
 
                     // “internally managed”
@@ -66,7 +66,7 @@ pub async fn handle_redeem_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
                     } else {
                         output.push_str("[Internally managed redeems]\n");
                         output.push_str(&format_table(&internal));
-                        output.push_str("\n");
+                        output.push('\n');
                     }
                     output
                 }
@@ -411,7 +411,7 @@ pub async fn handle_redeem_command(args: &[&str], bot_api: &Arc<dyn BotApi>) ->
 /// - If none, we return an error string.
 async fn resolve_singleton_redeem(bot_api: &Arc<dyn BotApi>, user_input: &str) -> Result<Redeem, String> {
     let matches = resolve_redeems_by_arg(bot_api, user_input).await
-        .map_err(|e| format!("{e}"))?;
+        .map_err(|e| e.to_string())?;
 
     match matches.len() {
         0 => Err(format!("No redeem found matching '{user_input}'.")),
@@ -509,7 +509,7 @@ fn format_table(redeems: &[Redeem]) -> String {
             out.push_str(&format!("{}{}", head, " ".repeat(pad)));
         }
         if i < headers.len() - 1 {
-            out.push_str(" ");
+            out.push(' ');
         }
     }
     out.push('\n');
@@ -526,7 +526,7 @@ fn format_table(redeems: &[Redeem]) -> String {
                 out.push_str(&format!("{}{}", cell, " ".repeat(pad)));
             }
             if i < row.len() - 1 {
-                out.push_str(" ");
+                out.push(' ');
             }
         }
         out.push('\n');
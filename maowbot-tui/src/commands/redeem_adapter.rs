@@ -1,17 +1,23 @@
 // Redeem command adapter for TUI
 use maowbot_common_ui::{GrpcClient, commands::redeem::RedeemCommands};
 use maowbot_proto::maowbot::common::Redeem;
+use maowbot_proto::maowbot::services::{RedeemScheduleInfo, SyncDirection, SyncResult};
 use std::io::{stdin, stdout, Write};
 use uuid::Uuid;
+use super::paging::PageArgs;
 
 pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
-        return "Usage: redeem <list|info|add|enable|disable|pause|unpause|setcost|setprompt|setplugin|setcommand|setinput|remove|sync>".to_string();
+        return "Usage: redeem <list|info|add|enable|disable|pause|unpause|setcost|setprompt|setplugin|setcommand|setinput|setapproval|remove|sync [both|to|from]|schedule|queue|approve|deny>".to_string();
     }
 
     match args[0].to_lowercase().as_str() {
         "list" => {
-            match RedeemCommands::list_redeems(client, Some("twitch-eventsub"), false, 100).await {
+            let page = match PageArgs::extract(args) {
+                Ok((page, _)) => page,
+                Err(e) => return format!("Error: {}", e),
+            };
+            match RedeemCommands::list_all_redeems(client, Some("twitch-eventsub"), false).await {
                 Ok(result) => {
                     if result.data.redeems.is_empty() {
                         return "No redeems found for 'twitch-eventsub'.".to_string();
@@ -25,6 +31,17 @@ pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String
                     // Partition: web-app managed (is_managed=false) vs internally managed (is_managed=true)
                     let (web_app, internal): (Vec<_>, Vec<_>) =
                         redeems.into_iter().partition(|rd| rd.metadata.get("is_managed") != Some(&"true".to_string()));
+                    
+                    // The window runs over web-app redeems then internal ones; internal
+                    // rows keep their full-list numbers so `redeem info <n>` still works.
+                    let total = web_app.len() + internal.len();
+                    let web_app_len = web_app.len();
+                    let windowed = page.apply(web_app.into_iter().chain(internal).collect::<Vec<_>>());
+                    let shown = windowed.len();
+                    let web_shown = web_app_len.saturating_sub(page.offset).min(shown);
+                    let first_internal_number = page.offset.saturating_sub(web_app_len) + 1;
+                    let mut web_app = windowed;
+                    let internal = web_app.split_off(web_shown);
 
                     let mut output = String::from("Current Redeems (twitch-eventsub):\n\n");
                     
@@ -33,8 +50,8 @@ pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String
                         output.push_str("[Web-app managed redeems]\n(no items)\n\n");
                     } else {
                         output.push_str("[Web-app managed redeems]\n");
-                        output.push_str(&format_table(&web_app, false));
-                        output.push_str("\n");
+                        output.push_str(&format_table(&web_app, None));
+                        output.push('\n');
                     }
                     
                     // Internally managed
@@ -42,8 +59,12 @@ pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String
                         output.push_str("[Internally managed redeems]\n(no items)\n\n");
                     } else {
                         output.push_str("[Internally managed redeems]\n");
-                        output.push_str(&format_table(&internal, true));
-                        output.push_str("\n");
+                        output.push_str(&format_table(&internal, Some(first_internal_number)));
+                        output.push('\n');
+                    }
+                    
+                    if let Some(footer) = page.footer(shown, total) {
+                        output.push_str(&format!("{}\n", footer));
                     }
                     
                     output
@@ -134,13 +155,21 @@ pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String
                 Ok(result) => {
                     let rd = &result.data.redeem;
                     
-                    // If we need to set active_offline, update the redeem
-                    if active_offline {
+                    // active_offline and dynamic_pricing aren't part of create, so update the redeem
+                    if active_offline || dynamic_pricing {
                         let mut updated_rd = rd.clone();
-                        updated_rd.metadata.insert("active_offline".to_string(), "true".to_string());
+                        let mut modes = Vec::new();
+                        if active_offline {
+                            updated_rd.metadata.insert("active_offline".to_string(), "true".to_string());
+                            modes.push("active offline");
+                        }
+                        if dynamic_pricing {
+                            updated_rd.metadata.insert("dynamic_pricing".to_string(), "true".to_string());
+                            modes.push("dynamic pricing");
+                        }
                         match RedeemCommands::update_redeem(client, &rd.redeem_id, updated_rd).await {
-                            Ok(_) => format!("Created redeem '{}' with cost {} (active offline).", rd.reward_name, rd.cost),
-                            Err(e) => format!("Created redeem '{}' but failed to set offline mode: {}", rd.reward_name, e),
+                            Ok(_) => format!("Created redeem '{}' with cost {} ({}).", rd.reward_name, rd.cost, modes.join(", ")),
+                            Err(e) => format!("Created redeem '{}' but failed to set {}: {}", rd.reward_name, modes.join(", "), e),
                         }
                     } else {
                         format!("Created redeem '{}' with cost {}.", rd.reward_name, rd.cost)
@@ -312,6 +341,31 @@ pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String
             }
         }
         
+        "setapproval" => {
+            if args.len() < 3 {
+                return "Usage: redeem setapproval <redeemNameOrUuidOrNumber> <true|false>".to_string();
+            }
+
+            let requires_approval = match args.last().unwrap().to_lowercase().as_str() {
+                "true" | "yes" | "1" | "on" => true,
+                "false" | "no" | "0" | "off" => false,
+                _ => return "Approval must be 'true' or 'false'.".to_string(),
+            };
+            let redeem_input = args[1..args.len()-1].join(" ");
+
+            match find_redeem(client, &redeem_input).await {
+                Ok(Some(rd)) => {
+                    match RedeemCommands::update_requires_approval(client, "twitch-eventsub", &rd.reward_name, requires_approval).await {
+                        Ok(_) if requires_approval => format!("Redemptions of '{}' now wait for approval (redeem queue).", rd.reward_name),
+                        Ok(_) => format!("Redemptions of '{}' now run right away.", rd.reward_name),
+                        Err(e) => format!("Error updating approval: {}", e),
+                    }
+                }
+                Ok(None) => format!("Redeem '{}' not found.", redeem_input),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+
         "remove" => {
             if args.len() < 2 {
                 return "Usage: redeem remove <redeemNameOrUuidOrNumber>".to_string();
@@ -346,24 +400,94 @@ pub async fn handle_redeem_command(args: &[&str], client: &GrpcClient) -> String
         }
         
         "sync" => {
-            match RedeemCommands::sync_redeems(client, "twitch-eventsub").await {
+            let direction = match args.get(1).map(|d| d.to_lowercase()).as_deref() {
+                None | Some("both") => SyncDirection::Bidirectional,
+                Some("to") | Some("push") => SyncDirection::ToPlatform,
+                Some("from") | Some("pull") | Some("import") => SyncDirection::FromPlatform,
+                Some(_) => return "Usage: redeem sync [both|to|from]".to_string(),
+            };
+
+            let preview = match RedeemCommands::sync_redeems(client, "twitch-eventsub", direction, true).await {
+                Ok(result) => result.data,
+                Err(e) => return format!("Error comparing redeems with Twitch: {}", e),
+            };
+            let pending = preview.changes.iter().filter(|c| c.change != "missing_on_twitch").count();
+            if preview.changes.is_empty() {
+                return "Redeems match Twitch: no changes.".to_string();
+            }
+
+            println!("{}", format_sync_changes(&preview.changes));
+            if pending == 0 {
+                return "Nothing to apply.".to_string();
+            }
+            println!("Apply {} change(s)? (y/n)", pending);
+            print!("> ");
+            let _ = stdout().flush();
+            let mut confirm = String::new();
+            let _ = stdin().read_line(&mut confirm);
+            if !confirm.trim().eq_ignore_ascii_case("y") {
+                return "Sync cancelled.".to_string();
+            }
+
+            match RedeemCommands::sync_redeems(client, "twitch-eventsub", direction, false).await {
                 Ok(result) => {
-                    if result.data.added_count == 0 && result.data.updated_count == 0 && result.data.removed_count == 0 {
-                        "Sync complete: No changes detected.".to_string()
-                    } else {
-                        format!(
-                            "Sync complete: {} added, {} updated, {} removed.",
-                            result.data.added_count,
-                            result.data.updated_count,
-                            result.data.removed_count
-                        )
+                    let mut out = format!(
+                        "Sync complete: {} added, {} updated, {} failed.",
+                        result.data.added_count,
+                        result.data.updated_count,
+                        result.data.error_count
+                    );
+                    for c in result.data.changes.iter().filter(|c| !c.success) {
+                        out.push_str(&format!("\n  {} '{}' failed: {}", c.change, c.reward_name, c.error_message));
                     }
+                    out
                 }
                 Err(e) => format!("Error syncing redeems: {}", e),
             }
         }
-        
-        _ => "Unknown redeem subcommand. Usage: redeem <list|info|add|enable|disable|pause|unpause|setcost|setprompt|setplugin|setcommand|setinput|remove|sync>".to_string(),
+
+        "schedule" | "schedules" => handle_schedule(args, client).await,
+
+        "queue" => match RedeemCommands::list_approvals(client).await {
+            Ok(pending) if pending.is_empty() => "No redemptions waiting for approval.".to_string(),
+            Ok(pending) => {
+                let now = chrono::Utc::now().timestamp();
+                let mut out = String::from("Waiting for approval:");
+                for (i, a) in pending.iter().enumerate() {
+                    let waited = a.requested_at.as_ref().map(|t| now - t.seconds).unwrap_or_default();
+                    out.push_str(&format!("\n  {}. {} from {} ({}s ago)", i + 1, a.reward_name, a.user_name, waited));
+                    if !a.user_input.is_empty() {
+                        out.push_str(&format!(": {}", a.user_input));
+                    }
+                }
+                out.push_str("\nUse 'redeem approve <n>' or 'redeem deny <n>'.");
+                out
+            }
+            Err(e) => format!("Error listing the queue: {}", e),
+        },
+
+        sub @ ("approve" | "deny") => {
+            let Some(input) = args.get(1) else {
+                return format!("Usage: redeem {} <queueNumber|approvalId>", sub);
+            };
+            let approval_id = match find_approval(client, input).await {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            if sub == "approve" {
+                match RedeemCommands::approve(client, &approval_id).await {
+                    Ok(a) => format!("Approved '{}' from {}.", a.reward_name, a.user_name),
+                    Err(e) => format!("Error approving: {}", e),
+                }
+            } else {
+                match RedeemCommands::deny(client, &approval_id).await {
+                    Ok(a) => format!("Denied '{}' from {}; points refunded.", a.reward_name, a.user_name),
+                    Err(e) => format!("Error denying: {}", e),
+                }
+            }
+        }
+
+        _ => "Unknown redeem subcommand. Usage: redeem <list|info|add|enable|disable|pause|unpause|setcost|setprompt|setplugin|setcommand|setinput|setapproval|remove|sync|schedule|queue|approve|deny>".to_string(),
     }
 }
 
@@ -412,6 +536,142 @@ async fn handle_pause_unpause(args: &[&str], client: &GrpcClient, pause: bool) -
     }
 }
 
+const SCHEDULE_USAGE: &str = "Usage:
+  redeem schedule list
+  redeem schedule add <name> <redeem> when <time [days] HH:MM-HH:MM [timezone] | category <name> | hype> then <enable|disable|cost N|multiply X> [priority N]
+  redeem schedule remove <name> <redeem>
+  redeem schedule enable|disable <name> <redeem>";
+
+// redeem schedule ...
+async fn handle_schedule(args: &[&str], client: &GrpcClient) -> String {
+    match args.get(1).map(|a| a.to_lowercase()).as_deref() {
+        None | Some("list") => match RedeemCommands::list_schedules(client).await {
+            Ok(resp) if resp.redeems.is_empty() => "No redeem schedules.".to_string(),
+            Ok(resp) => {
+                let mut out = String::from("Redeem schedules (* = applies now):");
+                for rd in &resp.redeems {
+                    out.push_str(&format!(
+                        "\n  {} => cost {} ({}), base {} ({})",
+                        rd.reward_name,
+                        rd.current_cost,
+                        if rd.current_enabled { "enabled" } else { "disabled" },
+                        rd.base_cost,
+                        if rd.base_enabled { "enabled" } else { "disabled" },
+                    ));
+                    for s in &rd.schedules {
+                        let action = match s.action.as_str() {
+                            "set_cost" => format!("cost {}", s.amount),
+                            "multiply_cost" => format!("cost x{}", s.amount),
+                            other => other.to_string(),
+                        };
+                        out.push_str(&format!(
+                            "\n    {} {:<16} when {} {} then {} (priority {}){}",
+                            if s.applies_now { "*" } else { " " },
+                            s.name,
+                            s.condition,
+                            s.condition_value,
+                            action,
+                            s.priority,
+                            if s.enabled { "" } else { " [off]" },
+                        ));
+                    }
+                }
+                out
+            }
+            Err(e) => format!("Error listing schedules: {}", e),
+        },
+
+        Some("add") => {
+            let position = |word: &str| args.iter().position(|a| a.eq_ignore_ascii_case(word));
+            let (Some(when), Some(then)) = (position("when"), position("then")) else {
+                return SCHEDULE_USAGE.to_string();
+            };
+            if args.len() < 4 || when < 4 || then <= when + 1 || then + 1 >= args.len() {
+                return SCHEDULE_USAGE.to_string();
+            }
+            let name = args[2];
+            let redeem_input = args[3..when].join(" ");
+            let condition = args[when + 1].to_lowercase();
+            let condition_value = args[when + 2..then].join(" ");
+
+            let mut action_args = &args[then + 1..];
+            let mut priority = 0;
+            if action_args.len() >= 2 && action_args[action_args.len() - 2].eq_ignore_ascii_case("priority") {
+                match action_args[action_args.len() - 1].parse::<i32>() {
+                    Ok(p) => priority = p,
+                    Err(_) => return "Priority must be a whole number.".to_string(),
+                }
+                action_args = &action_args[..action_args.len() - 2];
+            }
+            let (action, amount) = match action_args {
+                [a] if a.eq_ignore_ascii_case("enable") => ("enable", 0.0),
+                [a] if a.eq_ignore_ascii_case("disable") => ("disable", 0.0),
+                [a, n] if a.eq_ignore_ascii_case("cost") => match n.parse::<f64>() {
+                    Ok(n) => ("set_cost", n),
+                    Err(_) => return format!("Invalid cost '{}'.", n),
+                },
+                [a, n] if a.eq_ignore_ascii_case("multiply") => match n.trim_start_matches('x').parse::<f64>() {
+                    Ok(n) => ("multiply_cost", n),
+                    Err(_) => return format!("Invalid factor '{}'.", n),
+                },
+                _ => return SCHEDULE_USAGE.to_string(),
+            };
+
+            let rd = match find_redeem(client, &redeem_input).await {
+                Ok(Some(rd)) => rd,
+                Ok(None) => return format!("Redeem '{}' not found.", redeem_input),
+                Err(e) => return format!("Error: {}", e),
+            };
+            match RedeemCommands::add_schedule(client, &rd.redeem_id, name, &condition, &condition_value, action, amount, priority).await {
+                Ok(s) => format!("Added schedule '{}' to '{}'.", s.name, rd.reward_name),
+                Err(e) => format!("Error adding schedule: {}", e),
+            }
+        }
+
+        Some(sub @ ("remove" | "enable" | "disable")) => {
+            if args.len() < 4 {
+                return SCHEDULE_USAGE.to_string();
+            }
+            let schedule = match find_schedule(client, args[2], &args[3..].join(" ")).await {
+                Ok(s) => s,
+                Err(e) => return e,
+            };
+            let result = match sub {
+                "remove" => RedeemCommands::remove_schedule(client, &schedule.schedule_id).await
+                    .map(|_| format!("Removed schedule '{}'.", schedule.name)),
+                _ => RedeemCommands::set_schedule_enabled(client, &schedule.schedule_id, sub == "enable").await
+                    .map(|s| format!("Schedule '{}' {}.", s.name, if s.enabled { "enabled" } else { "disabled" })),
+            };
+            result.unwrap_or_else(|e| format!("Error updating schedule: {}", e))
+        }
+
+        _ => SCHEDULE_USAGE.to_string(),
+    }
+}
+
+/// A schedule by its name and its redeem's name or id.
+async fn find_schedule(client: &GrpcClient, name: &str, redeem_input: &str) -> Result<RedeemScheduleInfo, String> {
+    let resp = RedeemCommands::list_schedules(client).await.map_err(|e| format!("Error listing schedules: {}", e))?;
+    resp.redeems.into_iter()
+        .filter(|rd| rd.redeem_id == redeem_input || rd.reward_name.eq_ignore_ascii_case(redeem_input))
+        .flat_map(|rd| rd.schedules)
+        .find(|s| s.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("No schedule '{}' on '{}'.", name, redeem_input))
+}
+
+/// A queued redemption's id from its number in `redeem queue` or the id itself.
+async fn find_approval(client: &GrpcClient, input: &str) -> Result<String, String> {
+    if input.parse::<Uuid>().is_ok() {
+        return Ok(input.to_string());
+    }
+    let number: usize = input.parse().map_err(|_| format!("'{}' is neither a queue number nor an approval id.", input))?;
+    let pending = RedeemCommands::list_approvals(client).await.map_err(|e| format!("Error listing the queue: {}", e))?;
+    number.checked_sub(1)
+        .and_then(|i| pending.get(i))
+        .map(|a| a.approval_id.clone())
+        .ok_or_else(|| format!("No redemption #{} in the queue.", number))
+}
+
 // Find a redeem by name, UUID, or number (for internal redeems)
 async fn find_redeem(client: &GrpcClient, input: &str) -> Result<Option<Redeem>, String> {
     // First try to parse as UUID
@@ -422,7 +682,7 @@ async fn find_redeem(client: &GrpcClient, input: &str) -> Result<Option<Redeem>,
         }
     } else if let Ok(number) = input.parse::<usize>() {
         // If it's a number, use it as an index for internal redeems
-        match RedeemCommands::list_redeems(client, Some("twitch-eventsub"), false, 100).await {
+        match RedeemCommands::list_all_redeems(client, Some("twitch-eventsub"), false).await {
             Ok(result) => {
                 let internal_redeems: Vec<_> = result.data.redeems.into_iter()
                     .filter_map(|info| info.redeem)
@@ -467,6 +727,7 @@ fn format_redeem_details(rd: &Redeem) -> String {
          Active Offline:        {}\n\
          Is Managed:            {}\n\
          Input Required:        {}\n\
+         Requires Approval:     {}\n\
          Prompt:                {}\n\
          Plugin:                {}\n\
          Command:               {}\n\
@@ -479,6 +740,7 @@ fn format_redeem_details(rd: &Redeem) -> String {
         rd.metadata.get("active_offline").map(|s| s.as_str()).unwrap_or("false"),
         rd.metadata.get("is_managed").map(|s| s.as_str()).unwrap_or("false"),
         rd.metadata.get("input_required").map(|s| s.as_str()).unwrap_or("false"),
+        rd.metadata.get("requires_approval").map(|s| s.as_str()).unwrap_or("false"),
         rd.metadata.get("prompt").map(|s| s.as_str()).unwrap_or("-"),
         rd.metadata.get("plugin_id").map(|s| if s.is_empty() { "BUILTIN" } else { s }).unwrap_or("BUILTIN"),
         rd.metadata.get("command_name").map(|s| if s.is_empty() { "(none)" } else { s }).unwrap_or("(none)"),
@@ -487,7 +749,8 @@ fn format_redeem_details(rd: &Redeem) -> String {
 }
 
 // Format redeems as a table
-fn format_table(redeems: &[Redeem], enumerate: bool) -> String {
+/// Render redeems as a table; `first_number` adds a `#` column starting at that number.
+fn format_table(redeems: &[Redeem], first_number: Option<usize>) -> String {
     if redeems.is_empty() {
         return "(none)\n".to_string();
     }
@@ -505,8 +768,8 @@ fn format_table(redeems: &[Redeem], enumerate: bool) -> String {
         };
         
         let mut row = vec![];
-        if enumerate {
-            row.push(format!("{}", idx + 1));
+        if let Some(first) = first_number {
+            row.push(format!("{}", first + idx));
         }
         row.extend(vec![
             rd.reward_name.clone(),
@@ -522,7 +785,7 @@ fn format_table(redeems: &[Redeem], enumerate: bool) -> String {
     }
 
     // Determine column widths
-    let headers = if enumerate {
+    let headers = if first_number.is_some() {
         vec!["#", "Name", "Cost", "Actv", "Offl", "Input", "Plugin", "Command", "UUID"]
     } else {
         vec!["Name", "Cost", "Actv", "Offl", "Input", "Plugin", "Command", "UUID"]
@@ -554,7 +817,7 @@ fn format_table(redeems: &[Redeem], enumerate: bool) -> String {
                 out.push_str("  ");
             }
             // Right-align cost column
-            if (enumerate && i == 2) || (!enumerate && i == 1) {
+            if (first_number.is_some() && i == 2) || (first_number.is_none() && i == 1) {
                 out.push_str(&format!("{:>width$}", cell, width = col_widths[i]));
             } else {
                 out.push_str(&format!("{:<width$}", cell, width = col_widths[i]));
@@ -564,4 +827,25 @@ fn format_table(redeems: &[Redeem], enumerate: bool) -> String {
     }
 
     out
-}
\ No newline at end of file
+}
+
+/// The diff preview shown before `redeem sync` applies anything.
+fn format_sync_changes(changes: &[SyncResult]) -> String {
+    let mut out = String::from("Differences between the bot and Twitch:");
+    for c in changes {
+        let what = match c.change.as_str() {
+            "import" => "import from Twitch",
+            "link" => "relink to the Twitch reward with this title",
+            "create_on_twitch" => "create on Twitch",
+            "push" => "update Twitch",
+            "pull" => "update the bot",
+            "missing_on_twitch" => "deleted on Twitch (reported only)",
+            other => other,
+        };
+        out.push_str(&format!("\n  {} => {}", c.reward_name, what));
+        for d in &c.differences {
+            out.push_str(&format!("\n      {}", d));
+        }
+    }
+    out
+}
@@ -0,0 +1,257 @@
+// Chat responder command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::responders::ResponderCommands};
+use maowbot_proto::maowbot::services::{AddResponderRequest, ChatResponder, ResponderLines, UpdateResponderRequest};
+
+pub async fn handle_responder_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "list" => match ResponderCommands::list_responders(client).await {
+            Ok(responders) if responders.is_empty() => "No responders yet.".to_string(),
+            Ok(responders) => {
+                let mut out = String::new();
+                for responder in &responders {
+                    out.push_str(&format_responder(responder));
+                    out.push('\n');
+                }
+                out
+            }
+            Err(e) => format!("Error listing responders => {}", e),
+        },
+
+        "add" => add(&args[1..], client).await,
+        "edit" => edit(&args[1..], client).await,
+
+        "remove" | "delete" => {
+            let Some(name) = args.get(1) else {
+                return "Usage: responder remove <name>".to_string();
+            };
+            match ResponderCommands::delete_responder(client, name).await {
+                Ok(()) => format!("Removed responder '{}'.", name),
+                Err(e) => format!("Error removing responder => {}", e),
+            }
+        }
+
+        "enable" | "disable" => {
+            let Some(name) = args.get(1) else {
+                return format!("Usage: responder {} <name>", args[0].to_lowercase());
+            };
+            let enabled = args[0].eq_ignore_ascii_case("enable");
+            let request = UpdateResponderRequest { name: name.to_string(), enabled: Some(enabled), ..Default::default() };
+            match ResponderCommands::update_responder(client, request).await {
+                Ok(r) => format!("Responder '{}' is now {}.", r.name, if r.enabled { "enabled" } else { "disabled" }),
+                Err(e) => format!("Error updating responder => {}", e),
+            }
+        }
+
+        "test" => test(&args[1..], client).await,
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  responder list\n");
+    out.push_str("  responder add <name> <regex|any|all> <pattern...> [--say TEXT...]... [--platform P]\n");
+    out.push_str("                [--channel C] [--chance PCT] [--cooldown SECS] [--user-cooldown SECS]\n");
+    out.push_str("  responder edit <name> [pattern...] [--match K] [--say TEXT...]... [--platform P|any]\n");
+    out.push_str("                 [--channel C|any] [--chance PCT] [--cooldown SECS] [--user-cooldown SECS]\n");
+    out.push_str("  responder remove <name>\n");
+    out.push_str("  responder enable <name>\n");
+    out.push_str("  responder disable <name>\n");
+    out.push_str("  responder test [--platform P] [--channel C] <message...>\n");
+    out
+}
+
+/// Options shared by "add" and "edit".
+#[derive(Default)]
+struct ResponderOptions {
+    pattern: Vec<String>,
+    match_kind: Option<String>,
+    /// One entry per --say; None when no --say was given
+    responses: Option<Vec<String>>,
+    platform: Option<String>,
+    channel: Option<String>,
+    chance_percent: Option<i32>,
+    cooldown_secs: Option<i32>,
+    user_cooldown_secs: Option<i32>,
+}
+
+/// "any" clears a platform or channel filter.
+fn filter_value(value: &str) -> String {
+    if value.eq_ignore_ascii_case("any") { String::new() } else { value.to_string() }
+}
+
+fn parse_options(args: &[&str]) -> Result<ResponderOptions, String> {
+    let number = |flag: &str, value: &str| {
+        value.parse::<i32>().ok().filter(|n| *n >= 0)
+            .ok_or_else(|| format!("Invalid number '{}' for {}", value, flag))
+    };
+    let mut options = ResponderOptions::default();
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i];
+        if !flag.starts_with("--") {
+            options.pattern.push(flag.to_string());
+            i += 1;
+            continue;
+        }
+        if flag == "--say" {
+            // A response runs up to the next option
+            let words: Vec<&str> = args[i + 1..].iter().take_while(|w| !w.starts_with("--")).copied().collect();
+            if words.is_empty() {
+                return Err("Missing text for --say".to_string());
+            }
+            options.responses.get_or_insert_with(Vec::new).push(words.join(" "));
+            i += 1 + words.len();
+            continue;
+        }
+        let Some(value) = args.get(i + 1).copied() else {
+            return Err(format!("Missing value for {}", flag));
+        };
+        match flag {
+            "--match" => options.match_kind = Some(value.to_lowercase()),
+            "--platform" => options.platform = Some(filter_value(value)),
+            "--channel" => options.channel = Some(filter_value(value)),
+            "--chance" => options.chance_percent = Some(number(flag, value.trim_end_matches('%'))?),
+            "--cooldown" => options.cooldown_secs = Some(number(flag, value)?),
+            "--user-cooldown" => options.user_cooldown_secs = Some(number(flag, value)?),
+            _ => return Err(format!("Unknown option '{}'\n{}", flag, usage())),
+        }
+        i += 2;
+    }
+    Ok(options)
+}
+
+async fn add(args: &[&str], client: &GrpcClient) -> String {
+    if args.len() < 3 {
+        return "Usage: responder add <name> <regex|any|all> <pattern...> [options]".to_string();
+    }
+    let options = match parse_options(&args[2..]) {
+        Ok(options) => options,
+        Err(e) => return e,
+    };
+    let request = AddResponderRequest {
+        name: args[0].to_string(),
+        match_kind: args[1].to_string(),
+        pattern: options.pattern.join(" "),
+        responses: options.responses.unwrap_or_default(),
+        platform: options.platform.unwrap_or_default(),
+        channel: options.channel.unwrap_or_default(),
+        chance_percent: options.chance_percent.unwrap_or_default(),
+        // A short global cooldown unless asked otherwise, so a busy chat isn't answered line by line
+        cooldown_secs: options.cooldown_secs.unwrap_or(30),
+        user_cooldown_secs: options.user_cooldown_secs.unwrap_or_default(),
+    };
+    match ResponderCommands::add_responder(client, request).await {
+        Ok(responder) => format!("Added {}", format_responder(&responder)),
+        Err(e) => format!("Error adding responder => {}", e),
+    }
+}
+
+async fn edit(args: &[&str], client: &GrpcClient) -> String {
+    let Some(name) = args.first() else {
+        return "Usage: responder edit <name> [pattern...] [options]".to_string();
+    };
+    let options = match parse_options(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => return e,
+    };
+    let request = UpdateResponderRequest {
+        name: name.to_string(),
+        match_kind: options.match_kind,
+        pattern: (!options.pattern.is_empty()).then(|| options.pattern.join(" ")),
+        responses: options.responses.map(|lines| ResponderLines { lines }),
+        platform: options.platform,
+        channel: options.channel,
+        chance_percent: options.chance_percent,
+        cooldown_secs: options.cooldown_secs,
+        user_cooldown_secs: options.user_cooldown_secs,
+        enabled: None,
+    };
+    match ResponderCommands::update_responder(client, request).await {
+        Ok(responder) => format!("Updated {}", format_responder(&responder)),
+        Err(e) => format!("Error updating responder => {}", e),
+    }
+}
+
+async fn test(args: &[&str], client: &GrpcClient) -> String {
+    let (mut platform, mut channel) = (String::new(), String::new());
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i] {
+            "--platform" | "--channel" => {
+                let Some(value) = args.get(i + 1) else {
+                    return format!("Missing value for {}", args[i]);
+                };
+                if args[i] == "--platform" {
+                    platform = value.to_string();
+                } else {
+                    channel = value.to_string();
+                }
+                i += 2;
+            }
+            word => {
+                words.push(word);
+                i += 1;
+            }
+        }
+    }
+    if words.is_empty() {
+        return "Usage: responder test [--platform P] [--channel C] <message...>".to_string();
+    }
+
+    match ResponderCommands::test_message(client, &platform, &channel, &words.join(" ")).await {
+        Ok(hits) if hits.is_empty() => "No responder matches.".to_string(),
+        Ok(hits) => {
+            let mut out = String::new();
+            for (i, hit) in hits.iter().enumerate() {
+                let response = if hit.response.is_empty() { "(event only)".to_string() } else { hit.response.clone() };
+                out.push_str(&format!(
+                    "{} {} (matched \"{}\") -> {}\n",
+                    if i == 0 { "*" } else { " " }, hit.name, hit.matched, response
+                ));
+            }
+            out.push_str("* answers first; the others only when it is cooling down or loses its roll.\n");
+            out
+        }
+        Err(e) => format!("Error testing message => {}", e),
+    }
+}
+
+fn format_responder(r: &ChatResponder) -> String {
+    let mut scope = Vec::new();
+    if !r.platform.is_empty() {
+        scope.push(r.platform.clone());
+    }
+    if !r.channel.is_empty() {
+        scope.push(r.channel.clone());
+    }
+    let last_hit = r.last_hit_at.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+        .map(|t| format!(", last {}", t.format("%Y-%m-%d %H:%M")))
+        .unwrap_or_default();
+    let mut out = format!(
+        "{}{} [{}] {} -> {} response(s), {}% chance, cooldown {}s/{}s per user, on {}, {} hit(s){}",
+        r.name,
+        if r.enabled { "" } else { " (disabled)" },
+        r.match_kind,
+        r.pattern,
+        r.responses.len(),
+        r.chance_percent,
+        r.cooldown_secs,
+        r.user_cooldown_secs,
+        if scope.is_empty() { "every platform".to_string() } else { scope.join(" ") },
+        r.hit_count,
+        last_hit
+    );
+    for response in &r.responses {
+        out.push_str(&format!("\n    \"{}\"", response));
+    }
+    out
+}
@@ -0,0 +1,109 @@
+// Stream session command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::stream_sessions::StreamSessionCommands};
+use maowbot_proto::maowbot::services::StreamSession;
+
+/// Sessions listed when no count is given.
+const DEFAULT_LIMIT: i32 = 10;
+const TOP_CHATTERS: i32 = 10;
+
+pub async fn handle_session_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "list" => {
+            let limit = match args.get(1).map(|l| l.parse::<i32>()) {
+                None => DEFAULT_LIMIT,
+                Some(Ok(n)) if n > 0 => n,
+                _ => return "Count must be a positive number.".to_string(),
+            };
+            match StreamSessionCommands::list_sessions(client, limit).await {
+                Ok(sessions) if sessions.is_empty() => "No stream sessions yet.".to_string(),
+                Ok(sessions) => {
+                    let mut out = String::from("Stream sessions:\n");
+                    for s in &sessions {
+                        out.push_str(&format!("  {}\n", format_summary(s)));
+                    }
+                    out
+                }
+                Err(e) => format!("Error listing sessions => {}", e),
+            }
+        }
+
+        "show" => {
+            let which = args.get(1).copied().unwrap_or("current");
+            match StreamSessionCommands::get_session(client, which, TOP_CHATTERS).await {
+                Ok(resp) => {
+                    let Some(s) = resp.session else { return "No such session.".to_string() };
+                    let stats = s.stats.unwrap_or_default();
+                    let mut out = format!("{}\n", format_summary(&s));
+                    if !s.title.is_empty() {
+                        out.push_str(&format!("  title:     {}\n", s.title));
+                    }
+                    if !s.category.is_empty() {
+                        out.push_str(&format!("  category:  {}\n", s.category));
+                    }
+                    out.push_str(&format!(
+                        "  chat:      {} messages from {} chatters\n",
+                        stats.chat_messages, stats.unique_chatters
+                    ));
+                    out.push_str(&format!(
+                        "  community: {} follows, {} subs, {} gifted, {} bits, {} raids, {} redemptions\n",
+                        stats.follows, stats.subs, stats.gifted_subs, stats.bits, stats.raids, stats.redemptions
+                    ));
+                    out.push_str(&format!(
+                        "  peak:      {} viewers, {} hype moments\n",
+                        stats.peak_viewers, stats.hype_moments
+                    ));
+                    if !resp.markers.is_empty() {
+                        out.push_str("Markers:\n");
+                        for m in &resp.markers {
+                            out.push_str(&format!("  {:>8}  {} ({})\n", format_duration(m.position_seconds as i64), m.description, m.source));
+                        }
+                    }
+                    if !resp.top_chatters.is_empty() {
+                        out.push_str("Top chatters:\n");
+                        for (i, c) in resp.top_chatters.iter().enumerate() {
+                            out.push_str(&format!("  {:>2}. {:<24} {:>6}\n", i + 1, c.display_name, c.messages));
+                        }
+                    }
+                    out
+                }
+                Err(e) => format!("Error fetching session => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    "Usage:\n  session list [count]\n  session show [current|last|<session id>]\n".to_string()
+}
+
+/// "2025-01-31 20:00   2:14:05  #channel  1234 msgs, 80 chatters, peak 95  [id]"
+fn format_summary(s: &StreamSession) -> String {
+    let started = s.started_at.as_ref()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0))
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let stats = s.stats.unwrap_or_default();
+    format!(
+        "{}  {:>8}  #{}  {} msgs, {} chatters, peak {}{}  [{}]",
+        started,
+        format_duration(s.duration_seconds),
+        s.channel,
+        stats.chat_messages,
+        stats.unique_chatters,
+        stats.peak_viewers,
+        if s.live { "  (live)" } else { "" },
+        s.session_id,
+    )
+}
+
+/// "H:MM:SS"
+fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!("{}:{:02}:{:02}", seconds / 3600, (seconds % 3600) / 60, seconds % 60)
+}
@@ -63,7 +63,7 @@ async fn simulate_redeem(args: &[&str], trigger: &EventTrigger) -> String {
     let account = args[0];
     let channel = args[1];
     let redeem_name = args[2];
-    let input = args.get(3).map(|s| *s);
+    let input = args.get(3).copied();
 
     match trigger.trigger_test_redeem(account, channel, redeem_name, input).await {
         Ok(_) => format!("✓ Triggered test redeem '{}' in {} as {}", redeem_name, channel, account),
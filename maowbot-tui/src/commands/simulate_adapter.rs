@@ -0,0 +1,83 @@
+// Simulate command adapter for TUI - publishes synthetic platform events via the server
+use maowbot_common_ui::{GrpcClient, commands::pipeline::PipelineCommands};
+
+pub async fn handle_simulate_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return "Usage: simulate <list|eventsub|discord|slash|<event>> ...  (see 'help simulate')".to_string();
+    }
+
+    match args[0] {
+        "list" => list_event_types(client).await,
+
+        "eventsub" | "twitch-eventsub" => {
+            if args.len() < 2 {
+                return "Usage: simulate eventsub <event_type> [overrides_json]".to_string();
+            }
+            simulate(client, "twitch-eventsub", args[1], &args[2..]).await
+        }
+
+        "discord" => {
+            // The event type is optional: `simulate discord {"text": "!ping"}`
+            let (event_type, rest) = match args.get(1) {
+                Some(t) if !t.starts_with('{') => (*t, &args[2..]),
+                _ => ("message", &args[1..]),
+            };
+            simulate(client, "discord", event_type, rest).await
+        }
+
+        "slash" => {
+            // `simulate slash lastseen user=bob` runs /lastseen as a Discord user would
+            let Some(command) = args.get(1) else {
+                return "Usage: simulate slash <command> [option=value ...]".to_string();
+            };
+            let mut options = serde_json::Map::new();
+            for arg in &args[2..] {
+                let Some((name, value)) = arg.split_once('=') else {
+                    return format!("Options are written name=value, not '{}'", arg);
+                };
+                options.insert(name.to_string(), value.into());
+            }
+            let overrides = serde_json::json!({ "command": command, "options": options }).to_string();
+            simulate(client, "discord", "slash", &[overrides.as_str()]).await
+        }
+
+        // Anything else is an EventSub type or shortcut, e.g. `simulate raid {"viewers": 500}`
+        event_type => simulate(client, "twitch-eventsub", event_type, &args[1..]).await,
+    }
+}
+
+async fn simulate(client: &GrpcClient, platform: &str, event_type: &str, json_args: &[&str]) -> String {
+    let overrides = json_args.join(" ");
+    match PipelineCommands::simulate_event(client, platform, event_type, &overrides).await {
+        Ok(result) => {
+            let mut out = format!("✓ {}\n", result.data.message);
+            if !result.data.event_json.is_empty() {
+                out.push_str(&format!("Event ({}):\n{}\n", result.data.event_type, result.data.event_json));
+            }
+            out
+        }
+        Err(e) => format!("✗ Failed to simulate {} event '{}': {}", platform, event_type, e),
+    }
+}
+
+async fn list_event_types(client: &GrpcClient) -> String {
+    match PipelineCommands::list_simulated_events(client).await {
+        Ok(result) => {
+            let mut out = String::from("Simulated event types:\n");
+            let mut current_platform = "";
+            for event in &result.data.event_types {
+                if event.platform != current_platform {
+                    current_platform = &event.platform;
+                    out.push_str(&format!("\n  [{}]\n", current_platform));
+                }
+                if event.shortcuts.is_empty() {
+                    out.push_str(&format!("    {}\n", event.event_type));
+                } else {
+                    out.push_str(&format!("    {:56} ({})\n", event.event_type, event.shortcuts.join(", ")));
+                }
+            }
+            out
+        }
+        Err(e) => format!("Error listing simulated event types: {}", e),
+    }
+}
@@ -1,5 +1,7 @@
-use maowbot_common_ui::{ProcessManager, ProcessType, GrpcClient, commands::config::ConfigCommands};
+use maowbot_common_ui::{ProcessManager, ProcessStatus, ProcessType, GrpcClient, commands::config::ConfigCommands};
+use maowbot_common_ui::service::{ServiceInstallOptions, ServiceManager, ServiceScope};
 use std::sync::Arc;
+use maowbot_proto::maowbot::services::RetentionReport;
 
 pub async fn handle_system_command(
     parts: &[&str],
@@ -7,13 +9,45 @@ pub async fn handle_system_command(
     client: Option<&GrpcClient>,
 ) -> Result<String, Box<dyn std::error::Error>> {
     if parts.is_empty() {
-        return Ok("Usage: system [overlay|server|shutdown] [start|stop|status]".to_string());
+        return Ok("Usage: system [overlay|server|shutdown|log|update|db|cert|service|install-service|uninstall-service] [start|stop|status]".to_string());
+    }
+
+    if parts[0] == "log" {
+        return match client {
+            Some(client) => Ok(handle_log_command(&parts[1..], client).await),
+            None => Ok("Cannot change log levels: not connected to gRPC service".to_string()),
+        };
+    }
+
+    if matches!(parts[0], "install-service" | "uninstall-service" | "service") {
+        return Ok(handle_service_command(parts).await);
+    }
+
+    if parts[0] == "db" {
+        return match client {
+            Some(client) => Ok(handle_db_command(&parts[1..], client).await),
+            None => Ok("Cannot manage the database: not connected to gRPC service".to_string()),
+        };
+    }
+
+    if parts[0] == "cert" {
+        return match client {
+            Some(client) => Ok(handle_cert_command(&parts[1..], client).await),
+            None => Ok("Cannot manage certificates: not connected to gRPC service".to_string()),
+        };
+    }
+
+    if parts[0] == "update" {
+        return match client {
+            Some(client) => Ok(handle_update_command(&parts[1..], client).await),
+            None => Ok("Cannot manage updates: not connected to gRPC service".to_string()),
+        };
     }
 
     // Handle shutdown command
     if parts[0] == "shutdown" {
         if let Some(client) = client {
-            let reason = parts.get(1).map(|s| *s);
+            let reason = parts.get(1).copied();
             let grace_period = parts.get(2).and_then(|s| s.parse::<i32>().ok());
             
             match ConfigCommands::shutdown_server(client, reason, grace_period).await {
@@ -46,12 +80,7 @@ pub async fn handle_system_command(
     if parts.len() < 2 {
         // Just show status
         let status = process_manager.get_status(process_type).await;
-        return Ok(format!(
-            "{:?} status: {}, PID: {:?}",
-            process_type,
-            if status.running { "Running" } else { "Stopped" },
-            status.pid
-        ));
+        return Ok(format_process_status(process_type, &status));
     }
 
     match parts[1] {
@@ -73,14 +102,374 @@ pub async fn handle_system_command(
         }
         "status" => {
             let status = process_manager.get_status(process_type).await;
-            Ok(format!(
-                "{:?} status: {}, PID: {:?}",
-                process_type,
-                if status.running { "Running" } else { "Stopped" },
-                status.pid
-            ))
+            Ok(format_process_status(process_type, &status))
         }
         _ => Ok(format!("Unknown command: {}. Use 'start', 'stop', or 'status'", parts[1])),
     }
     }
-}
\ No newline at end of file
+}
+
+fn format_process_status(process_type: ProcessType, status: &ProcessStatus) -> String {
+    let mut out = format!(
+        "{:?} status: {}, PID: {:?}",
+        process_type,
+        if status.running { "Running" } else { "Stopped" },
+        status.pid
+    );
+    if status.restarts > 0 {
+        out.push_str(&format!("\nRestarted after crashes: {} time(s)", status.restarts));
+    }
+    if let Some(report) = &status.last_crash_report {
+        out.push_str(&format!("\nLast crash report: {}", report.display()));
+    }
+    out
+}
+
+async fn handle_log_command(parts: &[&str], client: &GrpcClient) -> String {
+    let result = match parts {
+        [] | ["levels"] => ConfigCommands::get_log_levels(client).await,
+        ["set", module, level] => ConfigCommands::set_log_level(client, module, Some(level)).await,
+        ["reset", module] => ConfigCommands::set_log_level(client, module, None).await,
+        _ => return "Usage: system log [levels] | system log set <module> <level> | system log reset <module>".to_string(),
+    };
+
+    match result {
+        Ok(levels) => {
+            let mut out = format!("Log format: {}\n", levels.format);
+            out.push_str(&format!("Base filter: {}\n", levels.base_filter));
+            if levels.overrides.is_empty() {
+                out.push_str("Module overrides: (none)\n");
+            } else {
+                out.push_str("Module overrides:\n");
+                for (module, level) in &levels.overrides {
+                    out.push_str(&format!("  {} = {}\n", module, level));
+                }
+            }
+            out.push_str(&format!("Effective filter: {}", levels.effective_filter));
+            out
+        }
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+async fn handle_update_command(parts: &[&str], client: &GrpcClient) -> String {
+    const USAGE: &str = "Usage: system update [status] | system update check | system update apply [restart_delay_secs] | system update auto <on|off>";
+
+    let result = match parts {
+        [] | ["status"] => ConfigCommands::get_update_status(client).await,
+        ["check"] => ConfigCommands::check_for_update(client).await,
+        ["apply"] | ["apply", _] => {
+            let delay = match parts.get(1).map(|d| d.parse::<i32>()) {
+                Some(Ok(d)) => Some(d),
+                Some(Err(_)) => return USAGE.to_string(),
+                None => None,
+            };
+            return match ConfigCommands::apply_update(client, delay).await {
+                Ok(r) if r.installed => {
+                    let at = r.restart_at
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_else(|| "shortly".to_string());
+                    format!("{}\nRestart at: {}\nRunning platform connections are restored after the restart.", r.message, at)
+                }
+                Ok(r) => format!("{} ({})", r.message, r.version),
+                Err(e) => format!("Update failed: {}", e),
+            };
+        }
+        ["auto", value @ ("on" | "off")] => {
+            let enabled = if *value == "on" { "true" } else { "false" };
+            return match ConfigCommands::set_config(client, "updater.auto_update", enabled).await {
+                Ok(_) => format!("Automatic updates {}", if enabled == "true" { "enabled" } else { "disabled" }),
+                Err(e) => format!("Error: {}", e),
+            };
+        }
+        _ => return USAGE.to_string(),
+    };
+
+    match result {
+        Ok(status) => {
+            let mut out = format!("Version: {} ({})\n", status.current_version, status.target);
+            out.push_str(&format!("State: {}\n", status.phase));
+            match &status.latest_version {
+                Some(latest) if status.update_available => {
+                    out.push_str(&format!("Update available: {}", latest));
+                    if let Some(published) = status.published_at {
+                        out.push_str(&format!(" (published {})", published.format("%Y-%m-%d")));
+                    }
+                    out.push('\n');
+                    if !status.release_notes.is_empty() {
+                        out.push_str(&format!("Notes: {}\n", status.release_notes));
+                    }
+                }
+                Some(latest) => out.push_str(&format!("Latest release: {}\n", latest)),
+                None => out.push_str("Latest release: (not checked yet)\n"),
+            }
+            if let Some(checked) = status.last_checked {
+                out.push_str(&format!("Last checked: {}\n", checked.format("%Y-%m-%d %H:%M:%S UTC")));
+            }
+            if let Some(err) = &status.last_error {
+                out.push_str(&format!("Last error: {}\n", err));
+            }
+            out.push_str(&format!("Feed: {}\n", if status.feed_url.is_empty() { "(not set: config set updater.feed_url <url>)" } else { &status.feed_url }));
+            if !status.has_public_key {
+                out.push_str("Signing key: (not set: updates are refused until updater.public_key is set)\n");
+            }
+            out.push_str(&format!("Auto-update: {}", if status.auto_update { "on" } else { "off" }));
+            out
+        }
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+async fn handle_service_command(parts: &[&str]) -> String {
+    const USAGE: &str = "Usage: system install-service [--system [--user <account>]] [--db <url>] | system uninstall-service | system service <start|stop|status>";
+
+    let result = match parts {
+        ["install-service", rest @ ..] => {
+            let mut opts = ServiceInstallOptions::default();
+            let mut i = 0;
+            while i < rest.len() {
+                match rest[i] {
+                    "--system" => opts.scope = ServiceScope::System,
+                    "--db" if i + 1 < rest.len() => {
+                        opts.db_url = Some(rest[i + 1].to_string());
+                        i += 1;
+                    }
+                    "--user" if i + 1 < rest.len() => {
+                        opts.run_as = Some(rest[i + 1].to_string());
+                        i += 1;
+                    }
+                    _ => return USAGE.to_string(),
+                }
+                i += 1;
+            }
+            ServiceManager::install(&opts).await
+        }
+        ["uninstall-service"] => ServiceManager::uninstall().await,
+        ["service"] | ["service", "status"] => ServiceManager::status().await,
+        ["service", "start"] => ServiceManager::start().await,
+        ["service", "stop"] => ServiceManager::stop().await,
+        _ => return USAGE.to_string(),
+    };
+
+    match result {
+        Ok(out) => out,
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
+fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{} B", bytes) } else { format!("{:.1} {}", value, UNITS[unit]) }
+}
+
+fn format_integrity(out: &mut String, ok: bool, errors: &[String], warnings: &[String]) {
+    out.push_str(if ok { "Integrity: ok\n" } else { "Integrity: PROBLEMS FOUND\n" });
+    for e in errors {
+        out.push_str(&format!("  error: {}\n", e));
+    }
+    for w in warnings {
+        out.push_str(&format!("  warning: {}\n", w));
+    }
+}
+
+async fn handle_cert_command(parts: &[&str], client: &GrpcClient) -> String {
+    const USAGE: &str = "Usage: system cert [show] | system cert regenerate [san ...] | system cert issue <name> [out_dir]";
+
+    match parts {
+        [] | ["show"] => match ConfigCommands::get_certificate_info(client).await {
+            Ok(info) => format_certificate(&info),
+            Err(e) => format!("Error: {}", e),
+        },
+        ["regenerate", sans @ ..] => {
+            let sans = sans.iter().map(|s| s.trim_matches(',').to_string()).filter(|s| !s.is_empty()).collect();
+            match ConfigCommands::regenerate_certificate(client, sans).await {
+                Ok(info) => format!(
+                    "{}\nRestart the server to use it. Clients that trust certs/ca.crt need no changes.",
+                    format_certificate(&info)
+                ),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+        ["issue", name] | ["issue", name, _] => {
+            let dir = parts.get(2).map(|d| d.to_string()).unwrap_or_else(|| format!("certs/clients/{}", name));
+            let issued = match ConfigCommands::issue_client_certificate(client, name).await {
+                Ok(issued) => issued,
+                Err(e) => return format!("Error: {}", e),
+            };
+            let dir = std::path::Path::new(&dir);
+            let write = || -> std::io::Result<()> {
+                std::fs::create_dir_all(dir)?;
+                std::fs::write(dir.join("client.crt"), &issued.cert_pem)?;
+                std::fs::write(dir.join("client.key"), &issued.key_pem)?;
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(dir.join("client.key"), std::fs::Permissions::from_mode(0o600))?;
+                }
+                std::fs::write(dir.join("ca.crt"), &issued.ca_pem)
+            };
+            match write() {
+                Ok(()) => format!(
+                    "Issued client certificate '{}' ({})\nWritten to {}/ (client.crt, client.key, ca.crt)\n\
+                     Copy the folder to the client machine and set MAOWBOT_GRPC_CA=<folder>/ca.crt,\n\
+                     MAOWBOT_GRPC_CLIENT_CERT=<folder>/client.crt and MAOWBOT_GRPC_CLIENT_KEY=<folder>/client.key",
+                    name, issued.fingerprint, dir.display()
+                ),
+                Err(e) => format!("Certificate issued but could not be written to {}: {}", dir.display(), e),
+            }
+        }
+        _ => USAGE.to_string(),
+    }
+}
+
+fn format_certificate(info: &maowbot_common_ui::commands::config::CertificateInfo) -> String {
+    let mut out = format!(
+        "Issuer: {}\n",
+        if info.has_ca { "local CA (certs/ca.crt)" } else { "self-signed (legacy; run `system cert regenerate`)" }
+    );
+    out.push_str(&format!(
+        "Generated: {}\n",
+        info.generated_at
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    ));
+    if !info.fingerprint.is_empty() {
+        out.push_str(&format!("SHA-256: {}\n", info.fingerprint));
+    }
+    out.push_str(&format!(
+        "Mutual TLS: {}\n",
+        if info.require_client_cert { "required (tls.require_client_cert)" } else { "off" }
+    ));
+    if info.restart_required {
+        out.push_str("A new certificate was generated; restart the server to use it\n");
+    }
+    if info.sans.is_empty() {
+        out.push_str("Names: (unknown)");
+    } else {
+        out.push_str("Names:");
+        for san in &info.sans {
+            out.push_str(&format!("\n  {}", san));
+        }
+    }
+    out
+}
+
+async fn handle_db_command(parts: &[&str], client: &GrpcClient) -> String {
+    const USAGE: &str = "Usage: system db [status] | system db snapshot [label] | system db compact [--full] | system db check | system db retention [--apply]";
+
+    match parts {
+        [] | ["status"] => match ConfigCommands::get_database_status(client).await {
+            Ok(status) => {
+                let mut out = format!(
+                    "PostgreSQL {} ({})\n",
+                    status.server_version,
+                    if status.embedded { "bundled cluster in postgres/data" } else { "external server" }
+                );
+                out.push_str(&format!("Database size: {}\n", format_bytes(status.database_bytes)));
+                if status.embedded {
+                    out.push_str(&format!("WAL size: {}\n", format_bytes(status.wal_bytes)));
+                }
+                out.push_str(&format!(
+                    "Last compacted: {}\n",
+                    status.last_compacted_at
+                        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_else(|| "not since startup".to_string())
+                ));
+                match &status.integrity {
+                    Some(i) => format_integrity(&mut out, i.ok, &i.errors, &i.warnings),
+                    None => out.push_str("Integrity: not checked yet\n"),
+                }
+                if status.snapshots.is_empty() {
+                    out.push_str("Snapshots: (none)");
+                } else {
+                    out.push_str(&format!("Snapshots ({}):", status.snapshots.len()));
+                    for snap in &status.snapshots {
+                        let at = snap.created_at
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default();
+                        out.push_str(&format!("\n  {:<48} {:>10}  {}", snap.name, format_bytes(snap.size_bytes), at));
+                    }
+                }
+                out
+            }
+            Err(e) => format!("Error: {}", e),
+        },
+        ["snapshot"] | ["snapshot", _] => {
+            match ConfigCommands::create_database_snapshot(client, parts.get(1).copied()).await {
+                Ok(snap) => format!(
+                    "Snapshot written: postgres/snapshots/{} ({})\nRestore with: pg_restore --clean --if-exists -d <db url> postgres/snapshots/{}",
+                    snap.name, format_bytes(snap.size_bytes), snap.name
+                ),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+        ["compact"] | ["compact", "--full"] => {
+            let full = parts.len() == 2;
+            match ConfigCommands::compact_database(client, full).await {
+                Ok(r) => {
+                    let mut out = format!(
+                        "Compacted in {} ms\nDatabase: {} -> {}\nWAL: {} -> {}",
+                        r.duration_ms,
+                        format_bytes(r.database_bytes_before),
+                        format_bytes(r.database_bytes_after),
+                        format_bytes(r.wal_bytes_before),
+                        format_bytes(r.wal_bytes_after),
+                    );
+                    if r.log_rotated {
+                        out.push_str("\nserver.log rotated");
+                    }
+                    for w in &r.warnings {
+                        out.push_str(&format!("\nwarning: {}", w));
+                    }
+                    out
+                }
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+        ["check"] => match ConfigCommands::check_database_integrity(client).await {
+            Ok(report) => {
+                let mut out = String::new();
+                format_integrity(&mut out, report.ok, &report.errors, &report.warnings);
+                out.trim_end().to_string()
+            }
+            Err(e) => format!("Error: {}", e),
+        },
+        ["retention"] | ["retention", "--apply"] => {
+            let apply = parts.len() == 2;
+            match ConfigCommands::run_retention(client, !apply).await {
+                Ok(report) => format_retention(&report),
+                Err(e) => format!("Error: {}", e),
+            }
+        }
+        _ => USAGE.to_string(),
+    }
+}
+
+fn format_retention(report: &RetentionReport) -> String {
+    let limit = |n: i64, unit: &str| if n > 0 { format!("{} {}", n, unit) } else { "-".to_string() };
+    let mut out = String::from(if report.dry_run {
+        "Retention dry run (nothing deleted; 'system db retention --apply' deletes):\n"
+    } else {
+        "Retention applied:\n"
+    });
+    out.push_str(&format!("  {:<16} {:>10} {:>12} {:>12} {:>10} {:>10}\n", "table", "max age", "max rows", "rows", "by age", "by size"));
+    for t in &report.tables {
+        out.push_str(&format!(
+            "  {:<16} {:>10} {:>12} {:>12} {:>10} {:>10}\n",
+            t.table, limit(t.max_age_days, "days"), limit(t.max_rows, "rows"),
+            t.total_rows, t.deleted_by_age, t.deleted_by_size,
+        ));
+    }
+    let total: i64 = report.tables.iter().map(|t| t.deleted_by_age + t.deleted_by_size).sum();
+    out.push_str(&format!("{} {} rows", if report.dry_run { "Would delete" } else { "Deleted" }, total));
+    for e in &report.errors {
+        out.push_str(&format!("\nerror: {}", e));
+    }
+    out
+}
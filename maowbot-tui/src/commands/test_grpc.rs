@@ -24,9 +24,11 @@ pub async fn handle_test_grpc_command(args: &[&str]) -> String {
                     active_only: false,
                     platforms: vec![],
                     roles: vec![],
+                    name_prefix: String::new(),
                 }),
                 order_by: "created_at".to_string(),
                 descending: false,
+                read_mask: None,
             }).await {
                 Ok(response) => {
                     let resp = response.into_inner();
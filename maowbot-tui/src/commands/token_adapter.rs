@@ -0,0 +1,120 @@
+// API token command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::token::TokenCommands};
+
+pub async fn handle_token_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "l" | "list" => {
+            let include_revoked = args.contains(&"--all");
+            match TokenCommands::list_tokens(client, include_revoked).await {
+                Ok(tokens) if tokens.is_empty() => "No API tokens.".to_string(),
+                Ok(tokens) => {
+                    let fmt = |t: Option<chrono::DateTime<chrono::Utc>>| {
+                        t.map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "never".to_string())
+                    };
+                    let mut out = format!(
+                        "{:24} {:10} {:16} {:17} {:17}\n", "Name", "Role", "Workspace", "Created", "Last used"
+                    );
+                    for t in &tokens {
+                        let ws = if t.workspace.is_empty() { "(all)" } else { t.workspace.as_str() };
+                        out.push_str(&format!(
+                            "{:24} {:10} {:16} {:17} {:17}{}\n",
+                            t.name, t.role, ws, fmt(t.created_at), fmt(t.last_used_at),
+                            if t.revoked_at.is_some() { " (revoked)" } else { "" }
+                        ));
+                    }
+                    out
+                }
+                Err(e) => format!("Error listing tokens => {}", e),
+            }
+        }
+
+        "create" => {
+            if args.len() < 3 {
+                return "Usage: token create <name> <admin|moderator|readonly> [--workspace <name>]".to_string();
+            }
+            let workspace = match args.iter().position(|a| *a == "--workspace") {
+                Some(i) => match args.get(i + 1) {
+                    Some(ws) => Some(*ws),
+                    None => return "Usage: token create <name> <admin|moderator|readonly> [--workspace <name>]".to_string(),
+                },
+                None => None,
+            };
+            match TokenCommands::create_token(client, args[1], args[2], workspace).await {
+                Ok(created) => format!(
+                    "Created {} token '{}'{}. Copy it now, it will not be shown again:\n\n  {}\n\n\
+                     Clients send it via the {} environment variable.",
+                    created.token.role, created.token.name,
+                    if created.token.workspace.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" for workspace '{}'", created.token.workspace)
+                    },
+                    created.secret,
+                    maowbot_common_ui::grpc_client::API_TOKEN_ENV
+                ),
+                Err(e) => format!("Error creating token => {}", e),
+            }
+        }
+
+        "revoke" => {
+            if args.len() < 2 {
+                return "Usage: token revoke <name>".to_string();
+            }
+            match TokenCommands::revoke_token(client, args[1]).await {
+                Ok(()) => format!("Revoked token '{}'.", args[1]),
+                Err(e) => format!("Error revoking token => {}", e),
+            }
+        }
+
+        "audit" => {
+            let limit = match args.iter().position(|a| *a == "--limit") {
+                Some(i) => match args.get(i + 1).and_then(|n| n.parse::<i32>().ok()) {
+                    Some(n) if n > 0 => n,
+                    _ => return "Usage: token audit [--limit N]".to_string(),
+                },
+                None => 50,
+            };
+            match TokenCommands::audit_log(client, limit).await {
+                Ok(entries) if entries.is_empty() => "Audit log is empty.".to_string(),
+                Ok(entries) => {
+                    let mut out = String::new();
+                    for e in &entries {
+                        let when = e.created_at
+                            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default();
+                        let who = if e.client_name.is_empty() { "<anonymous>" } else { e.client_name.as_str() };
+                        let ws = if e.workspace.is_empty() { String::new() } else { format!(" [{}]", e.workspace) };
+                        out.push_str(&format!(
+                            "{} {:6} {:16} {}{}",
+                            when, if e.allowed { "ok" } else { "DENIED" }, who, e.method, ws
+                        ));
+                        if !e.reason.is_empty() {
+                            out.push_str(&format!(" ({})", e.reason));
+                        }
+                        out.push('\n');
+                    }
+                    out
+                }
+                Err(e) => format!("Error reading audit log => {}", e),
+            }
+        }
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  token l|list [--all]                             # list API tokens (--all includes revoked)\n");
+    out.push_str("  token create <name> <admin|moderator|readonly>   # create a token for a gRPC client\n");
+    out.push_str("      [--workspace <name>]                         # limit it to one workspace (not admin)\n");
+    out.push_str("  token revoke <name>                              # reject the token from now on\n");
+    out.push_str("  token audit [--limit N]                          # admin calls and denied requests\n");
+    out
+}
@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use maowbot_common::traits::api::{BotApi, BotConfigApi, TwitchApi};
+use maowbot_common::traits::api::BotApi;
 use crate::tui_module::TuiModule;
 
 /// Helper to require an active Twitch-IRC account name from the TUI state.
@@ -62,14 +62,14 @@ pub async fn handle_twitch_command(
             // Enter chat mode in this TUI.
             let mut st = tui_module.ttv_state.lock().unwrap();
             if st.joined_channels.is_empty() {
-                return "No channels joined. Use 'ttv join <channelName>' first.".to_string();
+                "No channels joined. Use 'ttv join <channelName>' first.".to_string()
             } else {
                 st.is_in_chat_mode = true;
                 st.current_channel_index = 0;
-                return format!(
+                format!(
                     "Chat mode enabled. Type '/quit' to exit. Current channel: {}",
                     st.joined_channels[0]
-                );
+                )
             }
         }
         _ => "Unrecognized ttv subcommand. Type `ttv` for usage.".to_string(),
@@ -1,5 +1,6 @@
 // Twitch command adapter for TUI
 use maowbot_common_ui::{GrpcClient, commands::twitch::TwitchCommands};
+use maowbot_proto::maowbot::services::ScheduleSegment;
 use crate::tui_module_simple::SimpleTuiModule;
 use std::sync::Arc;
 
@@ -26,6 +27,10 @@ pub async fn handle_twitch_command(
   ttv part <channelName>
   ttv msg <channelName> <message text>
   ttv chat
+  ttv marker [description]
+  ttv chapters [streamId|videoId]
+  ttv schedule [add|title|category|cancel|restore|remove]
+  ttv eventsub [list|repair]
 "#.to_string();
     }
 
@@ -60,16 +65,27 @@ pub async fn handle_twitch_command(
             // Enter chat mode in this TUI.
             let mut st = tui_module.ttv_state.lock().unwrap();
             if st.joined_channels.is_empty() {
-                return "No channels joined. Use 'ttv join <channelName>' first.".to_string();
+                "No channels joined. Use 'ttv join <channelName>' first.".to_string()
             } else {
                 st.is_in_chat_mode = true;
                 st.current_channel_index = 0;
-                return format!(
+                format!(
                     "Chat mode enabled. Type '/quit' to exit. Current channel: {}",
                     st.joined_channels[0]
-                );
+                )
             }
         }
+        "marker" => do_create_marker(&args[1..].join(" "), client).await,
+        "chapters" => match args.get(1) {
+            Some(id) => do_show_chapters(id, client).await,
+            None => do_list_chapters(client).await,
+        },
+        "schedule" => do_schedule(&args[1..], client).await,
+        "eventsub" => match args.get(1).copied() {
+            None | Some("list") => do_list_eventsub(client).await,
+            Some("repair") => do_repair_eventsub(client).await,
+            Some(_) => "Usage: ttv eventsub [list|repair]".to_string(),
+        },
         _ => "Unrecognized ttv subcommand. Type `ttv` for usage.".to_string(),
     }
 }
@@ -190,4 +206,211 @@ async fn do_send_message(
         }
         Err(e) => format!("Failed to send message: {}", e),
     }
-}
\ No newline at end of file
+}
+
+async fn do_create_marker(description: &str, client: &GrpcClient) -> String {
+    match TwitchCommands::create_stream_marker(client, description).await {
+        Ok(result) => match result.data.marker {
+            Some(m) => format!(
+                "Marker placed at {}s of stream {}: {}",
+                m.position_seconds, m.stream_id, m.description
+            ),
+            None => "Marker placed.".to_string(),
+        },
+        Err(e) => format!("Failed to place marker: {}", e),
+    }
+}
+
+async fn do_list_chapters(client: &GrpcClient) -> String {
+    match TwitchCommands::list_vod_chapters(client, 0).await {
+        Ok(result) => {
+            if result.data.chapters.is_empty() {
+                return "No VOD chapters exported yet.".to_string();
+            }
+            let mut out = String::from("Exported VOD chapters:\n");
+            for c in result.data.chapters {
+                let vod = if c.video_id.is_empty() { "no VOD".to_string() } else { format!("VOD {}", c.video_id) };
+                out.push_str(&format!(
+                    "  stream {} ({}) - {} marker(s) - {}\n",
+                    c.stream_id, vod, c.marker_count, c.title
+                ));
+            }
+            out.push_str("Use 'ttv chapters <id>' to print one.");
+            out
+        }
+        Err(e) => format!("Failed to list VOD chapters: {}", e),
+    }
+}
+
+async fn do_show_chapters(id: &str, client: &GrpcClient) -> String {
+    match TwitchCommands::get_vod_chapters(client, id).await {
+        Ok(result) => match result.data.chapters {
+            Some(c) => {
+                let header = if c.video_url.is_empty() { c.title } else { format!("{} ({})", c.title, c.video_url) };
+                format!("{}\n{}", header, c.chapters)
+            }
+            None => format!("No chapters for '{}'.", id),
+        },
+        Err(e) => format!("Failed to get VOD chapters: {}", e),
+    }
+}
+
+const SCHEDULE_USAGE: &str = "Usage:
+  ttv schedule [refresh]
+  ttv schedule add <YYYY-MM-DD> <HH:MM> <minutes> [weekly] <title...>   (time in UTC)
+  ttv schedule title <segmentId> <title...>
+  ttv schedule category <segmentId> <category name...>
+  ttv schedule cancel|restore <segmentId>
+  ttv schedule remove <segmentId>";
+
+async fn do_schedule(args: &[&str], client: &GrpcClient) -> String {
+    match args.first().copied() {
+        None => do_list_schedule(false, client).await,
+        Some("refresh") => do_list_schedule(true, client).await,
+        Some("add") => {
+            if args.len() < 5 {
+                return SCHEDULE_USAGE.to_string();
+            }
+            let start = match chrono::NaiveDateTime::parse_from_str(&format!("{} {}", args[1], args[2]), "%Y-%m-%d %H:%M") {
+                Ok(at) => at.and_utc(),
+                Err(_) => return format!("Couldn't read '{} {}' as YYYY-MM-DD HH:MM.", args[1], args[2]),
+            };
+            let Ok(minutes) = args[3].parse::<i32>() else {
+                return format!("'{}' isn't a number of minutes.", args[3]);
+            };
+            let (recurring, title) = match args[4] {
+                "weekly" => (true, args[5..].join(" ")),
+                _ => (false, args[4..].join(" ")),
+            };
+            let start_time = maowbot_proto::prost_types::Timestamp {
+                seconds: start.timestamp(),
+                nanos: 0,
+            };
+            match TwitchCommands::create_schedule_segment(client, start_time, minutes, &title, "", recurring).await {
+                Ok(result) => match result.data.segment {
+                    Some(s) => format!("Added to the schedule: {}", format_segment(&s)),
+                    None => "Added to the schedule.".to_string(),
+                },
+                Err(e) => format!("Failed to add schedule segment: {}", e),
+            }
+        }
+        Some(field @ ("title" | "category")) if args.len() >= 3 => {
+            let value = args[2..].join(" ");
+            let segment = if field == "title" {
+                ScheduleSegment { title: value, ..Default::default() }
+            } else {
+                ScheduleSegment { category: value, ..Default::default() }
+            };
+            update_segment(args[1], segment, field, client).await
+        }
+        Some(action @ ("cancel" | "restore")) if args.len() == 2 => {
+            let segment = ScheduleSegment { is_canceled: action == "cancel", ..Default::default() };
+            update_segment(args[1], segment, "is_canceled", client).await
+        }
+        Some("remove") if args.len() == 2 => {
+            match TwitchCommands::delete_schedule_segment(client, args[1]).await {
+                Ok(_) => format!("Removed schedule segment {}.", args[1]),
+                Err(e) => format!("Failed to remove schedule segment: {}", e),
+            }
+        }
+        _ => SCHEDULE_USAGE.to_string(),
+    }
+}
+
+async fn update_segment(id: &str, segment: ScheduleSegment, field: &str, client: &GrpcClient) -> String {
+    match TwitchCommands::update_schedule_segment(client, id, segment, &[field]).await {
+        Ok(result) => match result.data.segment {
+            Some(s) => format!("Updated: {}", format_segment(&s)),
+            None => "Updated.".to_string(),
+        },
+        Err(e) => format!("Failed to update schedule segment: {}", e),
+    }
+}
+
+async fn do_list_schedule(refresh: bool, client: &GrpcClient) -> String {
+    match TwitchCommands::list_schedule_segments(client, refresh).await {
+        Ok(result) => {
+            if result.data.segments.is_empty() {
+                return "The stream schedule is empty.".to_string();
+            }
+            let mut out = String::from("Stream schedule (UTC):\n");
+            for s in &result.data.segments {
+                out.push_str(&format!("  {}  [{}]\n", format_segment(s), s.segment_id));
+            }
+            out.trim_end().to_string()
+        }
+        Err(e) => format!("Failed to get the stream schedule: {}", e),
+    }
+}
+
+fn format_segment(s: &ScheduleSegment) -> String {
+    let when = s.start_time.as_ref()
+        .and_then(|t| chrono::DateTime::from_timestamp(t.seconds, 0))
+        .map(|at| at.format("%a %Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "?".to_string());
+    let mut line = format!("{} ({}m) {}", when, s.duration_minutes, s.title);
+    if !s.category.is_empty() {
+        line.push_str(&format!(" - {}", s.category));
+    }
+    if s.is_recurring {
+        line.push_str(" [weekly]");
+    }
+    if s.is_canceled {
+        line.push_str(" [canceled]");
+    }
+    line
+}
+
+async fn do_list_eventsub(client: &GrpcClient) -> String {
+    let list = match TwitchCommands::list_eventsub_subscriptions(client).await {
+        Ok(result) => result.data,
+        Err(e) => return format!("Failed to list EventSub subscriptions: {}", e),
+    };
+    let mut out = format!(
+        "EventSub subscriptions: {} (cost {}/{}), session {}\n",
+        list.subscriptions.len(), list.total_cost, list.max_total_cost, list.session_id
+    );
+    for s in &list.subscriptions {
+        let mut line = format!("  {:<56} v{:<2} {:<10} cost {}", s.r#type, s.version, s.status, s.cost);
+        if !s.current_session && s.transport == "websocket" {
+            line.push_str("  [old session]");
+        }
+        if !s.stale_reason.is_empty() {
+            line.push_str(&format!("  [stale: {}]", s.stale_reason));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    if !list.missing.is_empty() {
+        out.push_str(&format!("Missing: {}\n", list.missing.join(", ")));
+    }
+    let stale = list.subscriptions.iter().filter(|s| !s.stale_reason.is_empty()).count();
+    if stale > 0 || !list.missing.is_empty() {
+        out.push_str("Run `ttv eventsub repair` to fix.");
+    }
+    out.trim_end().to_string()
+}
+
+async fn do_repair_eventsub(client: &GrpcClient) -> String {
+    let report = match TwitchCommands::repair_eventsub_subscriptions(client).await {
+        Ok(result) => result.data,
+        Err(e) => return format!("Failed to repair EventSub subscriptions: {}", e),
+    };
+    let mut out = format!(
+        "EventSub repaired: {} created, {} unchanged, {} pruned (cost {}/{})\n",
+        report.created.len(), report.unchanged, report.pruned.len(), report.total_cost, report.max_total_cost
+    );
+    for created in &report.created {
+        out.push_str(&format!("  + {}\n", created));
+    }
+    for s in &report.pruned {
+        out.push_str(&format!("  - {} v{} ({})\n", s.r#type, s.version, s.stale_reason));
+    }
+    for failed in &report.failed {
+        out.push_str(&format!("  ! {}\n", failed));
+    }
+    if !report.over_budget.is_empty() {
+        out.push_str(&format!("Over the cost budget, not created: {}\n", report.over_budget.join(", ")));
+    }
+    out.trim_end().to_string()
+}
@@ -55,7 +55,7 @@ pub async fn handle_ttv_command(
             if args.len() < 2 {
                 return "Usage: ttv follow <channel>".to_string();
             }
-            let channel = args[1];
+            let _channel = args[1];
             
             // Follow/unfollow not available in current API
             "Follow command not implemented".to_string()
@@ -69,7 +69,7 @@ pub async fn handle_ttv_command(
             if args.len() < 2 {
                 return "Usage: ttv unfollow <channel>".to_string();
             }
-            let channel = args[1];
+            let _channel = args[1];
             
             // Follow/unfollow not available in current API
             "Unfollow command not implemented".to_string()
@@ -88,7 +88,7 @@ pub async fn handle_ttv_command(
             match TwitchCommands::get_stream_info(client, channel).await {
                 Ok(result) => {
                     if let Some(stream) = result.data.stream {
-                        let mut out = format!("Stream is LIVE!\n");
+                        let mut out = "Stream is LIVE!\n".to_string();
                         out.push_str(&format!("Title: {}\n", stream.title));
                         out.push_str(&format!("Game: {}\n", stream.game_name));
                         out.push_str(&format!("Viewers: {}\n", stream.viewer_count));
@@ -108,9 +108,9 @@ pub async fn handle_ttv_command(
             if args.len() < 3 {
                 return "Usage: ttv ban <channel> <username> [reason]".to_string();
             }
-            let channel = args[1];
-            let username = args[2];
-            let reason = if args.len() > 3 { args[3..].join(" ") } else { String::new() };
+            let _channel = args[1];
+            let _username = args[2];
+            let _reason = if args.len() > 3 { args[3..].join(" ") } else { String::new() };
             
             // Ban/unban not available in current API
             "Ban command not implemented".to_string()
@@ -124,8 +124,8 @@ pub async fn handle_ttv_command(
             if args.len() < 3 {
                 return "Usage: ttv unban <channel> <username>".to_string();
             }
-            let channel = args[1];
-            let username = args[2];
+            let _channel = args[1];
+            let _username = args[2];
             
             // Ban/unban not available in current API
             "Unban command not implemented".to_string()
@@ -1,6 +1,9 @@
 // Unified user command adapter for TUI - combines user and member functionality
-use maowbot_common_ui::{GrpcClient, commands::{user::{UserCommands, UserUpdates}, member::MemberCommands}};
+use maowbot_common_ui::{GrpcClient, commands::{user::{UserCommands, UserUpdates}, member::{MemberCommands, NewModerationAction}}};
+use maowbot_proto::maowbot::services::{AnalysisRecomputeRun, GetViewerCardResponse, ModerationAction, UserNote};
+use maowbot_proto::maowbot::common::Platform;
 use std::io::{stdin, stdout, Write};
+use super::paging::PageArgs;
 
 pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
     if args.is_empty() {
@@ -8,7 +11,7 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
                 Basic Operations:\n    \
                 add, remove, edit, info, list, search\n  \
                 Extended Operations:\n    \
-                chat, note, merge, roles, analysis".to_string();
+                card, chat, note, history, merge, roles, analysis, analyze".to_string();
     }
 
     match args[0] {
@@ -99,7 +102,7 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
                     
                     // Basic user info
                     let user = &result.user;
-                    output.push_str(&format!("User Information:\n"));
+                    output.push_str("User Information:\n");
                     output.push_str(&format!("  ID: {}\n", user.user_id));
                     output.push_str(&format!("  Username: {}\n", user.global_username));
                     output.push_str(&format!("  Active: {}\n", user.is_active));
@@ -137,13 +140,26 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
                     
                     // User analysis
                     if let Some(analysis) = &result.analysis {
-                        output.push_str(&format!("\nAnalysis:\n"));
+                        output.push_str("\nAnalysis:\n");
                         output.push_str(&format!("  Spam Score: {:.2}\n", analysis.spam_score));
                         output.push_str(&format!("  Quality Score: {:.2}\n", analysis.quality_score));
                         if !analysis.moderator_notes.is_empty() {
                             output.push_str(&format!("  Moderator Notes: {}\n", analysis.moderator_notes));
                         }
                     }
+
+                    if !result.notes.is_empty() {
+                        output.push_str("\nRecent Notes:\n");
+                        for note in &result.notes {
+                            output.push_str(&format_note_line(note));
+                        }
+                    }
+                    if !result.moderation_history.is_empty() {
+                        output.push_str("\nModeration History:\n");
+                        for action in &result.moderation_history {
+                            output.push_str(&format_action_line(action));
+                        }
+                    }
                     
                     output
                 }
@@ -152,6 +168,13 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
         }
         
         "list" => {
+            let (page, args) = match PageArgs::extract(args) {
+                Ok(parsed) => parsed,
+                Err(e) => return format!("Error: {}", e),
+            };
+            if page.is_set() {
+                return list_users_window(client, page).await;
+            }
             let page_size = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(20);
             let page_token = args.get(2).map(|s| s.to_string());
             
@@ -162,12 +185,7 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
                     } else {
                         let mut output = format!("Users ({} per page):\n", page_size);
                         for user in result.data.users {
-                            output.push_str(&format!(
-                                "  {} - {} [{}]\n",
-                                user.user_id,
-                                user.global_username,
-                                if user.is_active { "Active" } else { "Inactive" }
-                            ));
+                            output.push_str(&format_user_line(&user));
                         }
                         if result.data.has_more {
                             output.push_str(&format!("\nNext page token: {}\n", result.data.next_page_token));
@@ -180,24 +198,28 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
         }
         
         "search" => {
+            let (page, args) = match PageArgs::extract(args) {
+                Ok(parsed) => parsed,
+                Err(e) => return format!("Error: {}", e),
+            };
             if args.len() < 2 {
-                return "Usage: user search <query>".to_string();
+                return "Usage: user search <query> [--limit N] [--offset N]".to_string();
             }
             let query = args[1];
             
-            match UserCommands::search_users(client, query, 50).await {
+            match UserCommands::search_users(client, query, page.fetch_size(50) as i32).await {
                 Ok(result) => {
                     if result.data.users.is_empty() {
                         format!("No users found matching '{}'", query)
                     } else {
-                        let mut output = format!("Found {} users:\n", result.data.users.len());
-                        for user in result.data.users {
-                            output.push_str(&format!(
-                                "  {} - {} [{}]\n",
-                                user.user_id,
-                                user.global_username,
-                                if user.is_active { "Active" } else { "Inactive" }
-                            ));
+                        let total = (result.data.total_count.max(0) as usize).max(result.data.users.len());
+                        let users = page.apply(result.data.users);
+                        let mut output = format!("Found {} users:\n", total);
+                        for user in &users {
+                            output.push_str(&format_user_line(user));
+                        }
+                        if let Some(footer) = page.footer(users.len(), total) {
+                            output.push_str(&format!("{}\n", footer));
                         }
                         output
                     }
@@ -214,18 +236,20 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
             "Chat message functionality not yet implemented in gRPC services.".to_string()
         }
         
-        "note" => {
-            if args.len() < 3 {
-                return "Usage: user note <usernameOrUUID> <note text...>".to_string();
+        "card" => {
+            if args.len() < 2 {
+                return "Usage: user card <usernameOrUUID> [numMessages]".to_string();
             }
-            let identifier = args[1];
-            let note_text = args[2..].join(" ");
-            
-            match MemberCommands::add_moderator_note(client, identifier, &note_text).await {
-                Ok(_) => format!("Note updated for user '{}'", identifier),
-                Err(e) => format!("Error updating note: {}", e),
+            let limit = args.get(2).and_then(|n| n.parse().ok()).unwrap_or(0);
+            match MemberCommands::viewer_card(client, args[1], limit).await {
+                Ok(card) => format_viewer_card(&card),
+                Err(e) => format!("Error getting viewer card: {}", e),
             }
         }
+
+        "note" | "notes" => handle_note(&args[1..], client).await,
+
+        "history" => handle_history(&args[1..], client).await,
         
         "merge" => {
             if args.len() < 3 {
@@ -246,24 +270,24 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
             }
             
             let action = args[1];
-            let username = args[2];
+            let _username = args[2];
             
             match action {
                 "add" => {
                     if args.len() < 4 {
                         return "Usage: user roles add <username> <role>".to_string();
                     }
-                    let role = args[3];
+                    let _role = args[3];
                     // For now, roles need platform info which isn't provided here
-                    format!("Error: Role management requires platform specification. Use 'user roles add <username> <platform> <role>'")
+                    "Error: Role management requires platform specification. Use 'user roles add <username> <platform> <role>'".to_string()
                 }
                 "remove" => {
                     if args.len() < 4 {
                         return "Usage: user roles remove <username> <role>".to_string();
                     }
-                    let role = args[3];
+                    let _role = args[3];
                     // For now, roles need platform info which isn't provided here
-                    format!("Error: Role management requires platform specification. Use 'user roles remove <username> <platform> <role>'")
+                    "Error: Role management requires platform specification. Use 'user roles remove <username> <platform> <role>'".to_string()
                 }
                 "list" => {
                     // List roles would require getting all platform identities first
@@ -303,8 +327,319 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
             }
         }
         
+        "analyze" => {
+            const USAGE: &str = "Usage: user analyze --all [--days N] | --resume | status | cancel";
+            match args.get(1).copied() {
+                Some("--all") => {
+                    let days = match args.get(2..) {
+                        Some(["--days", n]) => match n.parse::<i32>() {
+                            Ok(n) if n > 0 => Some(n),
+                            _ => return "--days must be a positive number".to_string(),
+                        },
+                        Some([]) | None => None,
+                        _ => return USAGE.to_string(),
+                    };
+                    match MemberCommands::start_analysis_recompute(client, days, false).await {
+                        Ok(run) => format!(
+                            "Recomputing analysis for {} users in the background (run {}).\n\
+                             Progress is posted as system messages; see 'user analyze status'.",
+                            run.total_users, run.run_id
+                        ),
+                        Err(e) => format!("Error starting analysis: {}", e),
+                    }
+                }
+                Some("--resume") => match MemberCommands::start_analysis_recompute(client, None, true).await {
+                    Ok(run) => format!(
+                        "Resumed run {} at {}/{} users.",
+                        run.run_id, run.processed_users, run.total_users
+                    ),
+                    Err(e) => format!("Error resuming analysis: {}", e),
+                },
+                Some("status") => match MemberCommands::analysis_recompute_status(client).await {
+                    Ok(Some(run)) => format_recompute_run(&run),
+                    Ok(None) => "No analysis recompute has been run.".to_string(),
+                    Err(e) => format!("Error getting analysis status: {}", e),
+                },
+                Some("cancel") => match MemberCommands::cancel_analysis_recompute(client).await {
+                    Ok(run) => format!(
+                        "Cancelling run {} after its current batch ({}/{} users done). Resume with 'user analyze --resume'.",
+                        run.run_id, run.processed_users, run.total_users
+                    ),
+                    Err(e) => format!("Error cancelling analysis: {}", e),
+                },
+                _ => USAGE.to_string(),
+            }
+        }
+
         _ => {
             format!("Unknown user subcommand: {}\n\nUse 'help user' for available commands.", args[0])
         }
     }
-}
\ No newline at end of file
+}
+fn format_timestamp(ts: &Option<prost_types::Timestamp>) -> String {
+    ts.as_ref()
+        .and_then(|t| chrono::DateTime::<chrono::Utc>::from_timestamp(t.seconds, 0))
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn format_recompute_run(run: &AnalysisRecomputeRun) -> String {
+    let percent = if run.total_users > 0 { run.processed_users * 100 / run.total_users } else { 100 };
+    let mut out = format!("Analysis run {}: {}\n", run.run_id, run.status);
+    out.push_str(&format!("  Users: {}/{} ({}%)\n", run.processed_users, run.total_users, percent));
+    out.push_str(&format!("  Messages: {} to {}\n", format_timestamp(&run.since), format_timestamp(&run.until)));
+    out.push_str(&format!("  Started: {}  Updated: {}\n", format_timestamp(&run.started_at), format_timestamp(&run.updated_at)));
+    if run.finished_at.is_some() {
+        out.push_str(&format!("  Finished: {}\n", format_timestamp(&run.finished_at)));
+    }
+    if !run.error.is_empty() {
+        out.push_str(&format!("  Error: {}\n", run.error));
+    }
+    if matches!(run.status.as_str(), "cancelled" | "interrupted" | "failed") {
+        out.push_str("  Continue it with 'user analyze --resume'.\n");
+    }
+    out
+}
+
+fn format_viewer_card(card: &GetViewerCardResponse) -> String {
+    let mut out = String::new();
+    if let Some(user) = &card.user {
+        out.push_str(&format!("Viewer card: {} ({})\n", user.global_username, user.user_id));
+        out.push_str(&format!("  First seen: {}  Last seen: {}\n", format_timestamp(&user.created_at), format_timestamp(&user.last_seen)));
+    }
+
+    if !card.identities.is_empty() {
+        out.push_str("\nIdentities:\n");
+        for identity in &card.identities {
+            let platform = Platform::try_from(identity.platform)
+                .map(|p| p.as_str_name().to_lowercase())
+                .unwrap_or_else(|_| "unknown".to_string());
+            let name = if identity.platform_display_name.is_empty() {
+                &identity.platform_username
+            } else {
+                &identity.platform_display_name
+            };
+            out.push_str(&format!("  {:12} {}", platform.trim_start_matches("platform_"), name));
+            if !identity.platform_roles.is_empty() {
+                out.push_str(&format!(" [{}]", identity.platform_roles.join(", ")));
+            }
+            out.push('\n');
+        }
+    }
+
+    out.push_str("\nStanding:\n");
+    if card.has_twitch_status {
+        let follow = if card.followed_at.is_some() {
+            format!("since {}", format_timestamp(&card.followed_at))
+        } else {
+            "not following".to_string()
+        };
+        out.push_str(&format!("  Twitch follow: {}\n", follow));
+        let sub = match card.sub_tier.as_str() {
+            "" => "not subscribed".to_string(),
+            tier => format!(
+                "tier {}{}",
+                tier.trim_end_matches("000"),
+                if card.sub_is_gift { " (gifted)" } else { "" }
+            ),
+        };
+        out.push_str(&format!("  Twitch sub: {}\n", sub));
+    }
+    out.push_str(&format!("  Redeems: {} ({} points)\n", card.redeems_used, card.points_spent));
+    if let Some(analysis) = &card.analysis {
+        out.push_str(&format!("  Spam {:.2}  Quality {:.2}\n", analysis.spam_score, analysis.quality_score));
+        if analysis.bot_scored_at.is_some() {
+            out.push_str(&format!("  Bot score {:.2}", analysis.bot_score));
+            if !analysis.bot_signals.is_empty() {
+                out.push_str(&format!(" ({})", analysis.bot_signals.join(", ")));
+            }
+            out.push('\n');
+        }
+    }
+
+    if !card.notes.is_empty() {
+        out.push_str("\nNotes:\n");
+        for note in &card.notes {
+            out.push_str(&format_note_line(note));
+        }
+    }
+
+    if !card.recent_messages.is_empty() {
+        out.push_str("\nRecent chat:\n");
+        for msg in &card.recent_messages {
+            out.push_str(&format!("  {} [{} {}] {}\n", format_timestamp(&msg.timestamp), msg.platform, msg.channel, msg.text));
+        }
+    }
+
+    if card.ai_memory_count > 0 || !card.ai_notes.is_empty() {
+        out.push_str(&format!("\nAI memory: {} entries", card.ai_memory_count));
+        if card.last_ai_memory_at.is_some() {
+            out.push_str(&format!(", last {}", format_timestamp(&card.last_ai_memory_at)));
+        }
+        out.push('\n');
+        for snippet in &card.ai_memory_recent {
+            out.push_str(&format!("  \"{}\"\n", snippet));
+        }
+        if !card.ai_notes.is_empty() {
+            out.push_str(&format!("  AI notes: {}\n", card.ai_notes));
+        }
+    }
+
+    if !card.warnings.is_empty() {
+        out.push_str("\nIncomplete:\n");
+        for warning in &card.warnings {
+            out.push_str(&format!("  {}\n", warning));
+        }
+    }
+    out
+}
+
+fn format_note_line(note: &UserNote) -> String {
+    format!("  {} {} ({}): {}\n", format_timestamp(&note.created_at), note.note_id, note.author, note.note_text)
+}
+
+fn format_action_line(action: &ModerationAction) -> String {
+    let mut line = format!("  {} {:8} {}", format_timestamp(&action.created_at), action.action_type, action.platform);
+    if !action.channel.is_empty() {
+        line.push_str(&format!(" {}", action.channel));
+    }
+    if action.duration_seconds > 0 {
+        line.push_str(&format!(" ({}s)", action.duration_seconds));
+    }
+    if !action.category.is_empty() {
+        line.push_str(&format!(" [{}]", action.category));
+    }
+    line.push_str(&format!(" by {}", action.moderator));
+    if !action.reason.is_empty() {
+        line.push_str(&format!(": {}", action.reason));
+    }
+    line.push('\n');
+    line
+}
+
+/// `user note <user> <text...>`, `user note list <user> [limit]`, `user note delete <noteId>`
+async fn handle_note(args: &[&str], client: &GrpcClient) -> String {
+    let usage = "Usage:\n  user note <usernameOrUUID> <note text...>\n  user note list <usernameOrUUID> [limit]\n  user note delete <noteId>";
+    match args.first().copied() {
+        Some("list") => {
+            let Some(identifier) = args.get(1) else { return usage.to_string() };
+            let limit = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(20);
+            match MemberCommands::list_user_notes(client, identifier, limit).await {
+                Ok(notes) if notes.is_empty() => format!("No notes for '{}'.", identifier),
+                Ok(notes) => {
+                    let mut output = format!("Notes for '{}':\n", identifier);
+                    for note in &notes {
+                        output.push_str(&format_note_line(note));
+                    }
+                    output
+                }
+                Err(e) => format!("Error listing notes: {}", e),
+            }
+        }
+        Some("delete") | Some("remove") => {
+            let Some(note_id) = args.get(1) else { return usage.to_string() };
+            match MemberCommands::delete_user_note(client, note_id).await {
+                Ok(()) => format!("Note {} deleted.", note_id),
+                Err(e) => format!("Error deleting note: {}", e),
+            }
+        }
+        Some(identifier) if args.len() >= 2 => {
+            let note_text = args[1..].join(" ");
+            match MemberCommands::add_user_note(client, identifier, &note_text).await {
+                Ok(note) => format!("Note added for user '{}' ({}).", identifier, note.note_id),
+                Err(e) => format!("Error adding note: {}", e),
+            }
+        }
+        _ => usage.to_string(),
+    }
+}
+
+/// `user history <user> [limit]` or
+/// `user history add <user> <timeout|ban|unban|warning> <platform> [--channel C] [--duration S] [reason...]`
+async fn handle_history(args: &[&str], client: &GrpcClient) -> String {
+    let usage = "Usage:\n  user history <usernameOrUUID> [limit]\n  \
+                 user history add <usernameOrUUID> <timeout|ban|unban|warning> <platform> [--channel C] [--duration S] [reason...]";
+    match args.first().copied() {
+        Some("add") => {
+            if args.len() < 4 {
+                return usage.to_string();
+            }
+            let (identifier, action_type, platform) = (args[1], args[2], args[3]);
+            let mut channel = "";
+            let mut duration_seconds = 0;
+            let mut reason = Vec::new();
+            let mut i = 4;
+            while i < args.len() {
+                match args[i] {
+                    "--channel" | "--duration" => {
+                        let Some(value) = args.get(i + 1).copied() else {
+                            return format!("Missing value for {}", args[i]);
+                        };
+                        if args[i] == "--channel" {
+                            channel = value;
+                        } else {
+                            match value.parse::<i32>() {
+                                Ok(d) if d > 0 => duration_seconds = d,
+                                _ => return format!("Invalid duration '{}'", value),
+                            }
+                        }
+                        i += 2;
+                    }
+                    word => {
+                        reason.push(word);
+                        i += 1;
+                    }
+                }
+            }
+            let reason = reason.join(" ");
+            let action = NewModerationAction { action_type, platform, channel, reason: &reason, duration_seconds };
+            match MemberCommands::record_moderation_action(client, identifier, action).await {
+                Ok(action) => format!("Recorded {} for '{}' on {}.", action.action_type, identifier, action.platform),
+                Err(e) => format!("Error recording moderation action: {}", e),
+            }
+        }
+        Some(identifier) => {
+            let limit = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(20);
+            match MemberCommands::moderation_history(client, identifier, limit).await {
+                Ok(actions) if actions.is_empty() => format!("No moderation history for '{}'.", identifier),
+                Ok(actions) => {
+                    let mut output = format!("Moderation history for '{}':\n", identifier);
+                    for action in &actions {
+                        output.push_str(&format_action_line(action));
+                    }
+                    output
+                }
+                Err(e) => format!("Error getting moderation history: {}", e),
+            }
+        }
+        None => usage.to_string(),
+    }
+}
+
+fn format_user_line(user: &maowbot_proto::maowbot::common::User) -> String {
+    format!(
+        "  {} - {} [{}]\n",
+        user.user_id,
+        user.global_username,
+        if user.is_active { "Active" } else { "Inactive" }
+    )
+}
+
+/// `user list --limit/--offset`: fetch enough of the first page to cover the window.
+async fn list_users_window(client: &GrpcClient, page: PageArgs) -> String {
+    match UserCommands::list_users_upto(client, page.fetch_size(20), false).await {
+        Ok(result) => {
+            let total = (result.data.total_count.max(0) as usize).max(result.data.users.len());
+            let users = page.apply(result.data.users);
+            let mut output = String::from("Users:\n");
+            for user in &users {
+                output.push_str(&format_user_line(user));
+            }
+            if let Some(footer) = page.footer(users.len(), total) {
+                output.push_str(&format!("{}\n", footer));
+            }
+            output
+        }
+        Err(e) => format!("Error listing users: {}", e),
+    }
+}
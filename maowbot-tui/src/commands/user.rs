@@ -199,7 +199,7 @@ async fn user_info(typed_user_or_id: &str, bot_api: &Arc<dyn BotApi>) -> String
             out.push_str(&format!("created_at={}\n", c.created_at));
             out.push_str(&format!("updated_at={}\n", c.updated_at));
             out.push_str(&format!("additional_data={:?}\n", c.additional_data));
-            out.push_str("\n");
+            out.push('\n');
         }
     }
 
@@ -252,7 +252,7 @@ async fn user_list(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
 
         let total = all_users.len();
         let mut output = String::new();
-        let total_pages = (total + per_page - 1) / per_page;
+        let total_pages = total.div_ceil(per_page);
 
         for (page_index, chunk) in all_users.chunks(per_page).enumerate() {
             output.push_str(&format!(
@@ -314,7 +314,7 @@ async fn user_find_duplicates(bot_api: &Arc<dyn BotApi>) -> String {
     for user in all_users {
         if let Some(username) = &user.global_username {
             let key = username.to_lowercase();
-            groups.entry(key).or_insert_with(Vec::new).push(user);
+            groups.entry(key).or_default().push(user);
         }
     }
     
@@ -337,7 +337,7 @@ async fn user_find_duplicates(bot_api: &Arc<dyn BotApi>) -> String {
         out.push_str(&format!("Username: '{}' ({} duplicates)\n", username, users.len()));
         // Sort users by creation date to identify the oldest (primary) one
         let mut sorted_users = users;
-        sorted_users.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        sorted_users.sort_by_key(|a| a.created_at);
         
         for (idx, user) in sorted_users.iter().enumerate() {
             let marker = if idx == 0 { " [OLDEST/PRIMARY]" } else { "" };
@@ -350,7 +350,7 @@ async fn user_find_duplicates(bot_api: &Arc<dyn BotApi>) -> String {
                 marker
             ));
         }
-        out.push_str("\n");
+        out.push('\n');
     }
     
     out.push_str("To merge duplicates, use: user merge <primary-user-id> <duplicate-user-id> [<duplicate-user-id>...]\n");
@@ -396,8 +396,8 @@ async fn user_merge(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
     }
     
     // Show what will be merged
-    let mut confirmation = format!("Merge operation summary:\n\n");
-    confirmation.push_str(&format!("PRIMARY USER (will be kept):\n"));
+    let mut confirmation = "Merge operation summary:\n\n".to_string();
+    confirmation.push_str("PRIMARY USER (will be kept):\n");
     confirmation.push_str(&format!("  ID: {} | Username: {:?} | Created: {}\n\n", 
         primary_user.user_id, 
         primary_user.global_username,
@@ -443,7 +443,7 @@ async fn user_merge(args: &[&str], bot_api: &Arc<dyn BotApi>) -> String {
         sql_commands.push_str(&format!("DELETE FROM user_analysis WHERE user_id = '{}';\n", dup_id));
         sql_commands.push_str(&format!("UPDATE user_audit_logs SET user_id = '{}' WHERE user_id = '{}';\n", primary_id, dup_id));
         sql_commands.push_str(&format!("DELETE FROM users WHERE user_id = '{}';\n", dup_id));
-        sql_commands.push_str("\n");
+        sql_commands.push('\n');
     }
     
     sql_commands.push_str("COMMIT;\n");
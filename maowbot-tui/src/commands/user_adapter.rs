@@ -1,5 +1,5 @@
 // Adapter that uses common-ui commands and formats output for TUI
-use maowbot_common_ui::{GrpcClient, commands::{user::{UserCommands, UserUpdates}, CommandError}};
+use maowbot_common_ui::{GrpcClient, commands::user::{UserCommands, UserUpdates}};
 use std::io::{stdin, stdout, Write};
 
 pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
@@ -104,7 +104,7 @@ pub async fn handle_user_command(args: &[&str], client: &GrpcClient) -> String {
                         for identity in &result.data.identities {
                             output.push_str(&format!(
                                 "\n  - {} ({}):\n    Display Name: {}",
-                                identity.platform as i32,
+                                identity.platform,
                                 identity.platform_user_id,
                                 identity.platform_display_name
                             ));
@@ -177,7 +177,7 @@ async fn user_info(username_or_uuid: &str, client: &GrpcClient) -> String {
             for identity in identities {
                 output.push_str(&format!(
                     "\n  - {} ({}):\n    Display Name: {}\n    Active: {}",
-                    identity.platform as i32,
+                    identity.platform,
                     identity.platform_user_id,
                     identity.platform_display_name,
                     true // Platform identities don't have is_active field
@@ -200,6 +200,7 @@ async fn user_search(query: &str, client: &GrpcClient) -> String {
             page_size: 50,
             page_token: String::new(),
         }),
+        read_mask: None,
     };
     
     match client.user.clone().search_users(request).await {
@@ -241,9 +242,11 @@ async fn user_list(page_size: i32, client: &GrpcClient) -> String {
                 active_only: false,
                 platforms: vec![],
                 roles: vec![],
+                name_prefix: String::new(),
             }),
             order_by: "created_at".to_string(),
             descending: false,
+            read_mask: None,
         };
         
         match client.user.clone().list_users(request).await {
@@ -299,6 +302,7 @@ async fn find_user_by_name(username: &str, client: &GrpcClient) -> Option<ProtoU
             page_size: 1,
             page_token: String::new(),
         }),
+        read_mask: None,
     };
     
     if let Ok(response) = client.user.clone().search_users(request).await {
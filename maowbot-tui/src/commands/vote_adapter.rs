@@ -0,0 +1,103 @@
+// Crowd control vote command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::crowd_vote::CrowdVoteCommands};
+use maowbot_proto::maowbot::services::CrowdVote;
+
+pub async fn handle_vote_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return usage();
+    }
+
+    let (rest, channel) = match take_channel(&args[1..]) {
+        Ok(split) => split,
+        Err(e) => return e,
+    };
+    let channel = channel.as_deref();
+
+    match args[0].to_lowercase().as_str() {
+        "start" => start(&rest, channel, client).await,
+
+        "status" => match CrowdVoteCommands::get(client, channel).await {
+            Ok(vote) => format_vote(&vote),
+            Err(e) => format!("Error loading vote => {}", e),
+        },
+
+        "end" => match CrowdVoteCommands::end(client, channel).await {
+            Ok(vote) => format_vote(&vote),
+            Err(e) => format!("Error ending vote => {}", e),
+        },
+
+        "cancel" => match CrowdVoteCommands::cancel(client, channel).await {
+            Ok(vote) => format!("Cancelled \"{}\" in #{}.", vote.title, vote.channel),
+            Err(e) => format!("Error cancelling vote => {}", e),
+        },
+
+        _ => usage(),
+    }
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  vote start <seconds> <title...> | <label>[=control] | <label>[=control] ... [--channel C]\n");
+    out.push_str("  vote status [--channel C]   # the open vote, or the last one\n");
+    out.push_str("  vote end [--channel C]      # decide now\n");
+    out.push_str("  vote cancel [--channel C]\n");
+    out
+}
+
+/// Pulls `--channel C` out of the arguments.
+fn take_channel<'a>(args: &[&'a str]) -> Result<(Vec<&'a str>, Option<String>), String> {
+    let mut rest = Vec::new();
+    let mut channel = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--channel" {
+            let Some(value) = args.get(i + 1) else {
+                return Err("Missing value for --channel".to_string());
+            };
+            channel = Some(value.to_string());
+            i += 2;
+        } else {
+            rest.push(args[i]);
+            i += 1;
+        }
+    }
+    Ok((rest, channel))
+}
+
+async fn start(args: &[&str], channel: Option<&str>, client: &GrpcClient) -> String {
+    let Some(seconds) = args.first().and_then(|s| s.parse::<u32>().ok()) else {
+        return format!("The first argument must be the vote length in seconds.\n{}", usage());
+    };
+    let joined = args[1..].join(" ");
+    let mut parts = joined.split('|').map(str::trim);
+    let title = parts.next().unwrap_or_default().to_string();
+    let options: Vec<(String, Option<String>)> = parts
+        .filter(|p| !p.is_empty())
+        .map(|p| match p.split_once('=') {
+            Some((label, control)) => (label.trim().to_string(), Some(control.trim().to_string())),
+            None => (p.to_string(), None),
+        })
+        .collect();
+    if title.is_empty() || options.len() < 2 {
+        return "A vote needs a title and at least two options separated by '|'.".to_string();
+    }
+
+    match CrowdVoteCommands::start(client, channel, &title, seconds, options).await {
+        Ok(vote) => format!("Started {}", format_vote(&vote)),
+        Err(e) => format!("Error starting vote => {}", e),
+    }
+}
+
+fn format_vote(v: &CrowdVote) -> String {
+    let mut out = format!("\"{}\" in #{} - {}", v.title, v.channel, v.status.replace('_', " "));
+    if !v.winner.is_empty() {
+        out.push_str(&format!(" - winner: {}", v.winner));
+    }
+    out.push('\n');
+    for (i, o) in v.options.iter().enumerate() {
+        let control = if o.control.is_empty() { String::new() } else { format!(" -> {}", o.control) };
+        out.push_str(&format!("  #{} {}{}: {} vote(s), weight {}\n", i + 1, o.label, control, o.votes, o.weight));
+    }
+    out
+}
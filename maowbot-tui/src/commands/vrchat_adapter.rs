@@ -44,7 +44,7 @@ pub async fn handle_vrchat_command(args: &[&str], client: &GrpcClient) -> String
                     Err(e) => format!("Error => {}", e),
                 };
             } else {
-                return show_vrchat_usage();
+                show_vrchat_usage()
             }
         }
         "instance" => {
@@ -65,10 +65,153 @@ pub async fn handle_vrchat_command(args: &[&str], client: &GrpcClient) -> String
                 Err(e) => format!("Error setting vrchat account => {}", e),
             }
         }
+        "session" | "sessions" => {
+            let result = if args.get(1).is_some_and(|a| a.eq_ignore_ascii_case("check")) {
+                VRChatCommands::check_sessions(client).await
+            } else {
+                VRChatCommands::list_sessions(client).await
+            };
+            match result {
+                Ok(sessions) => format_sessions(&sessions),
+                Err(e) => format!("Error => {}", e),
+            }
+        }
+        "here" => {
+            // "vrchat here [refresh]" => friends in the streamer's instance
+            let refresh = args.get(1).is_some_and(|a| a.eq_ignore_ascii_case("refresh"));
+            match VRChatCommands::get_presence(client, refresh).await {
+                Ok(presence) => format_presence(&presence),
+                Err(e) => format!("Error => {}", e),
+            }
+        }
+        "group" => handle_group_command(&args[1..], client).await,
+        "login" => {
+            // "vrchat login [accountName]" => log an expired account back in
+            let credential = match VRChatCommands::find_credential(client, args.get(1).copied()).await {
+                Ok(c) => c,
+                Err(e) => return format!("Error => {}", e),
+            };
+            match crate::commands::account_adapter::vrchat_relogin(client, &credential).await {
+                Ok(_) => {
+                    // Recheck right away so `vrchat session` shows the new state
+                    let _ = VRChatCommands::check_sessions(client).await;
+                    format!("VRChat account '{}' logged in again.", credential.user_name)
+                }
+                Err(e) => format!("VRChat login failed => {}", e),
+            }
+        }
         _ => show_vrchat_usage(),
     }
 }
 
+/// "vrchat group [requests [refresh] | approve <user> | reject <user> | post [--silent] <title> | <text>]"
+async fn handle_group_command(args: &[&str], client: &GrpcClient) -> String {
+    match args.first().map(|a| a.to_lowercase()).as_deref() {
+        None | Some("requests") => {
+            let refresh = args.get(1).is_some_and(|a| a.eq_ignore_ascii_case("refresh"));
+            match VRChatCommands::list_group_requests(client, refresh).await {
+                Ok(requests) => format_group_requests(&requests),
+                Err(e) => format!("Error => {}", e),
+            }
+        }
+        Some(action @ ("approve" | "reject")) => {
+            if args.len() < 2 {
+                return format!("Usage: vrchat group {} <usr_id or display name>", action);
+            }
+            let user = args[1..].join(" ");
+            let accept = action == "approve";
+            match VRChatCommands::respond_group_request(client, &user, accept).await {
+                Ok(r) => format!(
+                    "{} join request from {} ({}).",
+                    if accept { "Approved" } else { "Rejected" },
+                    r.display_name,
+                    r.user_id
+                ),
+                Err(e) => format!("Error => {}", e),
+            }
+        }
+        Some("post") => {
+            let silent = args.get(1).is_some_and(|a| *a == "--silent");
+            let rest = args[if silent { 2 } else { 1 }..].join(" ");
+            let Some((title, text)) = rest.split_once('|') else {
+                return "Usage: vrchat group post [--silent] <title> | <text>".to_string();
+            };
+            match VRChatCommands::post_group_announcement(client, title.trim(), text.trim(), !silent).await {
+                Ok(post_id) => format!("Posted to the VRChat group ({}).", post_id),
+                Err(e) => format!("Error => {}", e),
+            }
+        }
+        _ => "Usage: vrchat group [requests [refresh] | approve <user> | reject <user> | post [--silent] <title> | <text>]".to_string(),
+    }
+}
+
+fn format_group_requests(r: &maowbot_proto::maowbot::services::ListVrChatGroupRequestsResponse) -> String {
+    if r.group_id.is_empty() {
+        return "No VRChat group checked yet (set vrchat.group.id, then 'vrchat group requests refresh').".to_string();
+    }
+    let group = if r.group_name.is_empty() { &r.group_id } else { &r.group_name };
+    if r.requests.is_empty() {
+        return format!("No pending join requests for '{}'.", group);
+    }
+    let mut out = format!("Join requests for '{}' ({}):\n", group, r.requests.len());
+    for req in &r.requests {
+        out.push_str(&format!("  {} ({})", req.display_name, req.user_id));
+        if let Some(dt) = req.requested_at.as_ref().and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0)) {
+            out.push_str(&format!(" - {}", dt.format("%Y-%m-%d %H:%M UTC")));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn format_presence(p: &maowbot_proto::maowbot::services::GetVrChatPresenceResponse) -> String {
+    if p.location.is_empty() {
+        return "Not in a visible VRChat instance.".to_string();
+    }
+    let world = if p.world_name.is_empty() { &p.location } else { &p.world_name };
+    let mut out = format!("In '{}' with {} friend(s):\n", world, p.friends.len());
+    for f in &p.friends {
+        out.push_str(&format!("  {}", f.display_name));
+        if f.watched {
+            out.push_str(" *");
+        }
+        if let Some(dt) = f.since.as_ref().and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, 0)) {
+            out.push_str(&format!(" (since {})", dt.format("%H:%M UTC")));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn format_sessions(sessions: &[maowbot_proto::maowbot::services::VrChatSession]) -> String {
+    if sessions.is_empty() {
+        return "No VRChat sessions checked yet (try 'vrchat session check').".to_string();
+    }
+    let mut out = String::from("VRChat sessions:\n");
+    for s in sessions {
+        let state = match s.state.as_str() {
+            "valid" => "ok",
+            "2fa_required" => "needs 2FA",
+            "expired" => "EXPIRED",
+            _ => "unknown",
+        };
+        out.push_str(&format!("  {:<20} {}", s.user_name, state));
+        if !s.display_name.is_empty() {
+            out.push_str(&format!(" ({})", s.display_name));
+        }
+        if let Some(ts) = &s.checked_at {
+            if let Some(dt) = chrono::DateTime::from_timestamp(ts.seconds, 0) {
+                out.push_str(&format!(" | checked {}", dt.format("%Y-%m-%d %H:%M UTC")));
+            }
+        }
+        if !s.error.is_empty() {
+            out.push_str(&format!("\n      {}", s.error));
+        }
+        out.push('\n');
+    }
+    out
+}
+
 fn format_world_info(world: &maowbot_common_ui::commands::vrchat::VRChatWorldInfo) -> String {
     let mut out = String::new();
     out.push_str("World Name: ");
@@ -126,6 +269,22 @@ fn show_vrchat_usage() -> String {
 
   vrchat account <accountName>
     - sets the default VRChat account for built-in commands
+
+  vrchat here [refresh]
+    - lists friends in the streamer's current instance (* = on the watch list)
+
+  vrchat session [check]
+    - shows whether each VRChat login is still valid; 'check' asks VRChat now
+
+  vrchat login [accountName]
+    - logs an expired VRChat account back in (asks for the password and 2FA code)
+
+  vrchat group [requests [refresh]]
+    - lists pending join requests for the group in vrchat.group.id
+  vrchat group approve|reject <usr_id or display name>
+    - answers a join request
+  vrchat group post [--silent] <title> | <text>
+    - posts an announcement to the group (--silent skips member notifications)
 "#
         .to_string()
 }
\ No newline at end of file
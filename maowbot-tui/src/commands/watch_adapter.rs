@@ -0,0 +1,288 @@
+// Watch command adapter for TUI - live dashboards refreshed in place
+use maowbot_common_ui::GrpcClient;
+use maowbot_common_ui::commands::events::EventFeed;
+use maowbot_common_ui::commands::plugin::{MetricRates, PluginCommands, RuntimeMetricsSample};
+use maowbot_proto::maowbot::services::{UiEvent, UiEventKind};
+use std::io::{stdout, IsTerminal, Write};
+use std::time::Duration;
+
+const DEFAULT_INTERVAL_SECS: u64 = 2;
+/// Wait before reconnecting a dropped event stream.
+const EVENT_RETRY_SECS: u64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WatchTarget {
+    Status,
+    Osc,
+    Connections,
+}
+
+pub async fn handle_watch_command(args: &[&str], client: &GrpcClient) -> String {
+    let usage = "Usage: watch <status|osc|connections> [interval_secs] [--count N]\n       watch events [chat,alert,connection,osc] [--count N]";
+
+    if args.first() == Some(&"events") {
+        return watch_events(&args[1..], client, usage).await;
+    }
+
+    let target = match args.first() {
+        Some(&"status") => WatchTarget::Status,
+        Some(&"osc") => WatchTarget::Osc,
+        Some(&"connections") | Some(&"conn") => WatchTarget::Connections,
+        _ => return usage.to_string(),
+    };
+
+    let mut interval_secs = DEFAULT_INTERVAL_SECS;
+    let mut count: Option<u32> = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i] {
+            "--count" | "-n" => {
+                count = match args.get(i + 1).and_then(|s| s.parse::<u32>().ok()) {
+                    Some(n) if n > 0 => Some(n),
+                    _ => return usage.to_string(),
+                };
+                i += 2;
+            }
+            other => {
+                interval_secs = match other.parse::<u64>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return format!("Invalid interval '{}'. {}", other, usage),
+                };
+                i += 1;
+            }
+        }
+    }
+
+    // Only redraw in place when attached to a terminal; piped output gets one frame per sample
+    let redraw = stdout().is_terminal();
+    let interval = Duration::from_secs(interval_secs);
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    let mut previous: Option<RuntimeMetricsSample> = None;
+    let mut frames = 0;
+    loop {
+        let sample = match PluginCommands::get_runtime_metrics(client, target == WatchTarget::Status).await {
+            Ok(sample) => sample,
+            Err(e) => return format!("Error fetching runtime metrics: {}", e),
+        };
+        let rates = previous.as_ref().map(|p| sample.rates_since(p));
+
+        let mut frame = match target {
+            WatchTarget::Status => render_status(&sample, rates),
+            WatchTarget::Osc => render_osc(&sample, rates),
+            WatchTarget::Connections => render_connections(&sample),
+        };
+        if redraw {
+            frame = format!("\x1B[2J\x1B[H{}\n(refreshing every {}s - press Ctrl+C to stop)\n", frame, interval_secs);
+        }
+        print!("{}", frame);
+        let _ = stdout().flush();
+
+        previous = Some(sample);
+        frames += 1;
+        if count.is_some_and(|n| frames >= n) {
+            break;
+        }
+
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+
+    if redraw { "Stopped watching.".to_string() } else { String::new() }
+}
+
+/// `watch events`: prints the server's event stream as it arrives, reconnecting
+/// (and catching up on what was missed) if the connection drops.
+async fn watch_events(args: &[&str], client: &GrpcClient, usage: &str) -> String {
+    let mut kinds = Vec::new();
+    let mut count: Option<u32> = None;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i] {
+            "--count" | "-n" => {
+                count = match args.get(i + 1).and_then(|s| s.parse::<u32>().ok()) {
+                    Some(n) if n > 0 => Some(n),
+                    _ => return usage.to_string(),
+                };
+                i += 2;
+            }
+            list => {
+                for name in list.split(',').filter(|s| !s.is_empty()) {
+                    kinds.push(match name {
+                        "chat" => UiEventKind::Chat,
+                        "alert" | "alerts" => UiEventKind::Alert,
+                        "connection" | "connections" => UiEventKind::Connection,
+                        "osc" => UiEventKind::Osc,
+                        other => return format!("Unknown event kind '{}'. {}", other, usage),
+                    });
+                }
+                i += 1;
+            }
+        }
+    }
+
+    println!("Watching events (press Ctrl+C to stop)...");
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut feed = EventFeed::new(kinds);
+    let mut received = 0;
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            result = feed.next(client) => match result {
+                Ok(event) => {
+                    println!("{}", format_ui_event(&event));
+                    received += 1;
+                    if count.is_some_and(|n| received >= n) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    println!("(stream interrupted: {}; reconnecting in {}s)", e, EVENT_RETRY_SECS);
+                    tokio::select! {
+                        _ = &mut ctrl_c => break,
+                        _ = tokio::time::sleep(Duration::from_secs(EVENT_RETRY_SECS)) => {}
+                    }
+                }
+            }
+        }
+    }
+    format!("Stopped watching events ({} received).", received)
+}
+
+fn format_ui_event(event: &UiEvent) -> String {
+    let time = event.timestamp.as_ref()
+        .and_then(|t| chrono::DateTime::from_timestamp(t.seconds, t.nanos as u32))
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+    let field = |key: &str| event.fields.get(key).map(String::as_str).unwrap_or_default();
+    let replayed = if event.replayed { " (missed)" } else { "" };
+    let body = match UiEventKind::try_from(event.kind) {
+        Ok(UiEventKind::Chat) => {
+            let name = if field("display_name").is_empty() { field("username") } else { field("display_name") };
+            format!("[chat] {} {}: {}", field("channel"), name, field("message"))
+        }
+        Ok(UiEventKind::Connection) if event.event_type == "ops.alert" => {
+            format!("[ops {}] {} {}", field("severity"), field("title"), field("message")).trim_end().to_string()
+        }
+        Ok(UiEventKind::Connection) => match field("state") {
+            "" => format!("[connection] {} {}: {}", field("platform"), field("user"), field("error")),
+            state => format!("[connection] {} {}: {}", field("platform"), field("user"), state),
+        },
+        Ok(UiEventKind::Osc) => format!(
+            "[osc] {} - {} in, {} out in the last {}s",
+            if field("running") == "true" { "running" } else { "stopped" },
+            field("received"), field("sent"), field("interval_secs")
+        ),
+        Ok(UiEventKind::Gap) => "[gap] some events were missed and can't be replayed".to_string(),
+        _ => {
+            let mut details: Vec<String> = ["user", "amount", "viewers", "reward", "tier", "message"].into_iter()
+                .filter(|k| !field(k).is_empty())
+                .map(|k| format!("{}={}", k, field(k)))
+                .collect();
+            if details.is_empty() {
+                details.push(field("channel").to_string());
+            }
+            format!("[alert] {} {}", event.event_type, details.join(" "))
+        }
+    };
+    format!("{} {}{}", time, body, replayed)
+}
+
+fn render_status(sample: &RuntimeMetricsSample, rates: Option<MetricRates>) -> String {
+    let mut out = String::new();
+    out.push_str("=== MaowBot Status ===\n");
+    out.push_str(&format!("Uptime:       {}\n", format_uptime(sample.uptime_seconds)));
+    out.push_str(&format!(
+        "Events:       {} total, {}\n",
+        sample.events_total,
+        format_rate(rates.map(|r| r.events_per_sec))
+    ));
+    out.push_str(&format!(
+        "OSC:          {} (in {}, out {})\n",
+        if sample.osc_running { "running" } else { "stopped" },
+        format_rate(rates.map(|r| r.osc_in_per_sec)),
+        format_rate(rates.map(|r| r.osc_out_per_sec))
+    ));
+
+    if let Some(outbound) = sample.outbound {
+        out.push_str(&format!(
+            "Outbound:     {} sent, {} repeats dropped, {} rate-limited\n",
+            outbound.sent, outbound.duplicates_dropped, outbound.rate_limited
+        ));
+    }
+
+    let connected = sample.connections.iter().filter(|c| c.connected).count();
+    out.push_str(&format!("Connections:  {}/{} connected\n", connected, sample.connections.len()));
+    for conn in sample.connections.iter().filter(|c| !c.connected) {
+        out.push_str(&format!("  ✗ {} ({})\n", conn.platform, conn.account));
+    }
+
+    if !sample.event_counts.is_empty() {
+        let mut counts: Vec<_> = sample.event_counts.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        out.push_str("\nTop event types:\n");
+        for (event_type, count) in counts.into_iter().take(5) {
+            out.push_str(&format!("  {:40} {:>8}\n", event_type, count));
+        }
+    }
+    out
+}
+
+fn render_osc(sample: &RuntimeMetricsSample, rates: Option<MetricRates>) -> String {
+    let mut out = String::new();
+    out.push_str("=== OSC ===\n");
+    out.push_str(&format!("State:     {}\n", if sample.osc_running { "running" } else { "stopped" }));
+    out.push_str(&format!(
+        "Received:  {:>10} packets  {}\n",
+        sample.osc_packets_received,
+        format_rate(rates.map(|r| r.osc_in_per_sec))
+    ));
+    out.push_str(&format!(
+        "Sent:      {:>10} packets  {}\n",
+        sample.osc_packets_sent,
+        format_rate(rates.map(|r| r.osc_out_per_sec))
+    ));
+    out
+}
+
+fn render_connections(sample: &RuntimeMetricsSample) -> String {
+    let mut out = String::new();
+    out.push_str("=== Platform Connections ===\n");
+    if sample.connections.is_empty() {
+        out.push_str("No platform accounts configured.\n");
+        return out;
+    }
+    out.push_str("Platform        | Account                  | State\n");
+    out.push_str("----------------|--------------------------|-------------\n");
+    for conn in &sample.connections {
+        out.push_str(&format!(
+            "{:15} | {:24} | {}\n",
+            conn.platform,
+            conn.account,
+            if conn.connected { "✓ connected" } else { "✗ disconnected" }
+        ));
+    }
+    out
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    match rate {
+        Some(r) => format!("{:.1}/s", r),
+        None => "-/s".to_string(),
+    }
+}
+
+fn format_uptime(seconds: i64) -> String {
+    let (days, rem) = (seconds / 86_400, seconds % 86_400);
+    let (hours, rem) = (rem / 3600, rem % 3600);
+    let (minutes, secs) = (rem / 60, rem % 60);
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, minutes, secs)
+    } else {
+        format!("{:02}h {:02}m {:02}s", hours, minutes, secs)
+    }
+}
@@ -0,0 +1,140 @@
+// Workspace command adapter for TUI
+use maowbot_common_ui::{GrpcClient, commands::workspace::WorkspaceCommands};
+use std::io::{stdin, stdout, Write};
+
+pub async fn handle_workspace_command(args: &[&str], client: &GrpcClient) -> String {
+    if args.is_empty() {
+        return current(client);
+    }
+
+    match args[0].to_lowercase().as_str() {
+        "l" | "list" => {
+            match WorkspaceCommands::list_workspaces(client).await {
+                Ok(result) => {
+                    let current = result.current.unwrap_or_else(|| "default".to_string());
+                    let mut out = String::new();
+                    for ws in &result.workspaces {
+                        let marker = if ws.name == current || ws.workspace_id == current { "*" } else { " " };
+                        let created = ws.created_at
+                            .map(|t| t.format("%Y-%m-%d").to_string())
+                            .unwrap_or_default();
+                        out.push_str(&format!(
+                            "{} {:20} {:24} {}\n", marker, ws.name, ws.display_name, created
+                        ));
+                    }
+                    out
+                }
+                Err(e) => format!("Error listing workspaces => {}", e),
+            }
+        }
+
+        "create" => {
+            if args.len() < 2 {
+                return "Usage: workspace create <name> [display name]".to_string();
+            }
+            let display_name = if args.len() > 2 { Some(args[2..].join(" ")) } else { None };
+            match WorkspaceCommands::create_workspace(client, args[1], display_name.as_deref()).await {
+                Ok(ws) => format!(
+                    "Created workspace '{}'. Use 'workspace use {}' to switch to it.", ws.name, ws.name
+                ),
+                Err(e) => format!("Error creating workspace => {}", e),
+            }
+        }
+
+        "use" | "switch" => {
+            if args.len() < 2 {
+                return "Usage: workspace use <name>".to_string();
+            }
+            match WorkspaceCommands::use_workspace(client, args[1]).await {
+                Ok(ws) => format!("Now using workspace '{}'.", ws.name),
+                Err(e) => format!("Error switching workspace => {}", e),
+            }
+        }
+
+        "delete" => {
+            if args.len() < 2 {
+                return "Usage: workspace delete <name> [--yes]".to_string();
+            }
+            let name = args[1];
+            let confirmed = args.contains(&"--yes") || args.contains(&"-y");
+            if !confirmed {
+                println!(
+                    "Delete workspace '{}' with all of its commands, redeems, config and channel routes? (y/n): ",
+                    name
+                );
+                print!("> ");
+                let _ = stdout().flush();
+
+                let mut line = String::new();
+                let _ = stdin().read_line(&mut line);
+
+                if line.trim().to_lowercase() != "y" {
+                    return "Workspace deletion cancelled.".to_string();
+                }
+            }
+            match WorkspaceCommands::delete_workspace(client, name).await {
+                Ok(()) => format!("Deleted workspace '{}'.", name),
+                Err(e) => format!("Error deleting workspace => {}", e),
+            }
+        }
+
+        "channels" => {
+            match WorkspaceCommands::list_channels(client).await {
+                Ok(channels) if channels.is_empty() => {
+                    "No channels are routed; every channel uses the default workspace.".to_string()
+                }
+                Ok(channels) => {
+                    let mut out = format!("{:10} {:24} {}\n", "Platform", "Channel", "Workspace");
+                    for c in &channels {
+                        out.push_str(&format!("{:10} {:24} {}\n", c.platform, c.channel, c.workspace));
+                    }
+                    out
+                }
+                Err(e) => format!("Error listing channel routes => {}", e),
+            }
+        }
+
+        "channel" => {
+            match args.get(1).map(|a| a.to_lowercase()).as_deref() {
+                Some("add") if args.len() >= 4 => {
+                    let workspace = client.workspace().unwrap_or_else(|| "default".to_string());
+                    match WorkspaceCommands::assign_channel(client, &workspace, args[2], args[3]).await {
+                        Ok(()) => format!(
+                            "{} channel '{}' now uses workspace '{}'.", args[2], args[3], workspace
+                        ),
+                        Err(e) => format!("Error assigning channel => {}", e),
+                    }
+                }
+                Some("remove") if args.len() >= 4 => {
+                    match WorkspaceCommands::unassign_channel(client, args[2], args[3]).await {
+                        Ok(()) => format!("{} channel '{}' now uses the default workspace.", args[2], args[3]),
+                        Err(e) => format!("Error removing channel route => {}", e),
+                    }
+                }
+                _ => "Usage: workspace channel add|remove <platform> <channel>".to_string(),
+            }
+        }
+
+        "current" => current(client),
+
+        _ => usage(),
+    }
+}
+
+fn current(client: &GrpcClient) -> String {
+    format!("Current workspace: {}", client.workspace().unwrap_or_else(|| "default".to_string()))
+}
+
+fn usage() -> String {
+    let mut out = String::new();
+    out.push_str("Usage:\n");
+    out.push_str("  workspace [current]                     # show the workspace commands apply to\n");
+    out.push_str("  workspace l|list                        # list workspaces (* = current)\n");
+    out.push_str("  workspace create <name> [display name]  # add a workspace for another broadcaster\n");
+    out.push_str("  workspace use <name>                    # switch workspace for this session\n");
+    out.push_str("  workspace delete <name> [--yes]         # delete a workspace (move its credentials first)\n");
+    out.push_str("  workspace channels                      # chat channels routed to a workspace\n");
+    out.push_str("  workspace channel add <platform> <ch>   # answer <ch> with the current workspace\n");
+    out.push_str("  workspace channel remove <platform> <ch> # send <ch> back to the default workspace\n");
+    out
+}
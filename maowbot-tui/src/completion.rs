@@ -4,7 +4,7 @@ use rustyline::error::ReadlineError;
 use rustyline::highlight::{Highlighter, MatchingBracketHighlighter};
 use rustyline::hint::{Hinter, HistoryHinter};
 use rustyline::validate::{Validator, ValidationContext, ValidationResult};
-use rustyline::{Helper, Context};
+use rustyline::Context;
 use rustyline_derive::Helper;
 use std::borrow::Cow;
 
@@ -22,6 +22,12 @@ struct CommandInfo {
     description: String,
 }
 
+impl Default for TuiCompleter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TuiCompleter {
     pub fn new() -> Self {
         Self {
@@ -49,6 +55,11 @@ impl TuiCompleter {
                 subcommands: vec![],
                 description: "List all plugins".to_string(),
             },
+            CommandInfo {
+                name: "alias".to_string(),
+                subcommands: vec!["add".to_string(), "remove".to_string(), "show".to_string(), "list".to_string()],
+                description: "Command aliases and macros".to_string(),
+            },
             CommandInfo {
                 name: "quit".to_string(),
                 subcommands: vec![],
@@ -65,11 +76,14 @@ impl TuiCompleter {
                     "info".to_string(),
                     "search".to_string(),
                     "list".to_string(),
+                    "card".to_string(),
                     "chat".to_string(),
                     "note".to_string(),
+                    "history".to_string(),
                     "merge".to_string(),
                     "roles".to_string(),
                     "analysis".to_string(),
+                    "analyze".to_string(),
                 ],
                 description: "User management".to_string(),
             },
@@ -104,6 +118,8 @@ impl TuiCompleter {
                     "list".to_string(),
                     "show".to_string(),
                     "refresh".to_string(),
+                    "scopes".to_string(),
+                    "reauth".to_string(),
                 ],
                 description: "Account management".to_string(),
             },
@@ -115,6 +131,7 @@ impl TuiCompleter {
                     "status".to_string(),
                     "autostart".to_string(),
                     "chat".to_string(),
+                    "identity".to_string(),
                 ],
                 description: "Connection management".to_string(),
             },
@@ -124,7 +141,15 @@ impl TuiCompleter {
                 name: "command".to_string(),
                 subcommands: vec![
                     "list".to_string(),
+                    "stats".to_string(),
+                    "import".to_string(),
+                    "setresponse".to_string(),
                     "setcooldown".to_string(),
+                    "setusercooldown".to_string(),
+                    "setrolecooldown".to_string(),
+                    "setbypassmods".to_string(),
+                    "setprivate".to_string(),
+                    "setreply".to_string(),
                     "setwarnonce".to_string(),
                     "setrespond".to_string(),
                     "enable".to_string(),
@@ -141,6 +166,12 @@ impl TuiCompleter {
                     "cost".to_string(),
                     "enable".to_string(),
                     "disable".to_string(),
+                    "sync".to_string(),
+                    "schedule".to_string(),
+                    "setapproval".to_string(),
+                    "queue".to_string(),
+                    "approve".to_string(),
+                    "deny".to_string(),
                 ],
                 description: "Redeem management".to_string(),
             },
@@ -150,12 +181,171 @@ impl TuiCompleter {
                     "list".to_string(),
                     "get".to_string(),
                     "set".to_string(),
+                    "set-secret".to_string(),
                     "delete".to_string(),
+                    "schema".to_string(),
                     "export".to_string(),
                     "import".to_string(),
+                    "rotate-key".to_string(),
+                    "export-all".to_string(),
+                    "import-all".to_string(),
                 ],
                 description: "Configuration management".to_string(),
             },
+            CommandInfo {
+                name: "workspace".to_string(),
+                subcommands: vec![
+                    "list".to_string(),
+                    "create".to_string(),
+                    "use".to_string(),
+                    "delete".to_string(),
+                    "current".to_string(),
+                    "channels".to_string(),
+                    "channel".to_string(),
+                ],
+                description: "Workspace (broadcaster) selection".to_string(),
+            },
+            CommandInfo {
+                name: "token".to_string(),
+                subcommands: vec![
+                    "list".to_string(),
+                    "create".to_string(),
+                    "revoke".to_string(),
+                    "audit".to_string(),
+                ],
+                description: "API tokens and access audit".to_string(),
+            },
+            CommandInfo {
+                name: "audit".to_string(),
+                subcommands: vec!["list".to_string()],
+                description: "Audit log of state-changing calls".to_string(),
+            },
+            CommandInfo {
+                name: "chatlog".to_string(),
+                subcommands: vec![
+                    "search".to_string(),
+                    "lastseen".to_string(),
+                    "retention".to_string(),
+                    "activity".to_string(),
+                ],
+                description: "Chat history search and retention".to_string(),
+            },
+            CommandInfo {
+                name: "export".to_string(),
+                subcommands: vec![
+                    "chat".to_string(),
+                    "events".to_string(),
+                    "commands".to_string(),
+                ],
+                description: "Export chat, events or command uses to CSV/JSONL".to_string(),
+            },
+            CommandInfo {
+                name: "giveaway".to_string(),
+                subcommands: vec![
+                    "open".to_string(),
+                    "list".to_string(),
+                    "show".to_string(),
+                    "close".to_string(),
+                    "cancel".to_string(),
+                    "draw".to_string(),
+                    "reroll".to_string(),
+                ],
+                description: "Giveaways with draws and re-rolls".to_string(),
+            },
+            CommandInfo {
+                name: "vote".to_string(),
+                subcommands: vec![
+                    "start".to_string(),
+                    "status".to_string(),
+                    "end".to_string(),
+                    "cancel".to_string(),
+                ],
+                description: "Crowd control votes among OSC effects".to_string(),
+            },
+            CommandInfo {
+                name: "protect".to_string(),
+                subcommands: vec![
+                    "status".to_string(),
+                    "shield".to_string(),
+                    "lockdown".to_string(),
+                    "lift".to_string(),
+                    "incidents".to_string(),
+                    "incident".to_string(),
+                ],
+                description: "Raid defense and Shield Mode".to_string(),
+            },
+            CommandInfo {
+                name: "automod".to_string(),
+                subcommands: vec![
+                    "list".to_string(),
+                    "add".to_string(),
+                    "edit".to_string(),
+                    "remove".to_string(),
+                    "enable".to_string(),
+                    "disable".to_string(),
+                    "reset".to_string(),
+                    "test".to_string(),
+                    "offenses".to_string(),
+                    "pardon".to_string(),
+                ],
+                description: "Link and phrase moderation rules".to_string(),
+            },
+            CommandInfo {
+                name: "responder".to_string(),
+                subcommands: vec![
+                    "list".to_string(),
+                    "add".to_string(),
+                    "edit".to_string(),
+                    "remove".to_string(),
+                    "enable".to_string(),
+                    "disable".to_string(),
+                    "test".to_string(),
+                ],
+                description: "Regex and keyword chat responders".to_string(),
+            },
+            CommandInfo {
+                name: "alerting".to_string(),
+                subcommands: vec![
+                    "rules".to_string(),
+                    "add".to_string(),
+                    "set".to_string(),
+                    "remove".to_string(),
+                    "enable".to_string(),
+                    "disable".to_string(),
+                    "recent".to_string(),
+                    "test".to_string(),
+                ],
+                description: "Ops alert rules and recent alerts".to_string(),
+            },
+            CommandInfo {
+                name: "emotes".to_string(),
+                subcommands: vec![
+                    "top".to_string(),
+                    "sets".to_string(),
+                    "refresh".to_string(),
+                ],
+                description: "Emote usage stats and third-party emote sets".to_string(),
+            },
+            CommandInfo {
+                name: "session".to_string(),
+                subcommands: vec![
+                    "list".to_string(),
+                    "show".to_string(),
+                ],
+                description: "Stream sessions and per-stream stats".to_string(),
+            },
+            CommandInfo {
+                name: "language".to_string(),
+                subcommands: vec![
+                    "list".to_string(),
+                    "channel".to_string(),
+                    "keys".to_string(),
+                    "set".to_string(),
+                    "unset".to_string(),
+                    "import".to_string(),
+                ],
+                description: "Bot response languages".to_string(),
+            },
             
             // Platform-Specific
             CommandInfo {
@@ -167,6 +357,9 @@ impl TuiCompleter {
                     "msg".to_string(),
                     "chat".to_string(),
                     "default".to_string(),
+                    "marker".to_string(),
+                    "chapters".to_string(),
+                    "eventsub".to_string(),
                 ],
                 description: "Twitch-specific commands".to_string(),
             },
@@ -176,6 +369,11 @@ impl TuiCompleter {
                     "world".to_string(),
                     "avatar".to_string(),
                     "instance".to_string(),
+                    "account".to_string(),
+                    "here".to_string(),
+                    "session".to_string(),
+                    "login".to_string(),
+                    "group".to_string(),
                 ],
                 description: "VRChat integration".to_string(),
             },
@@ -220,7 +418,9 @@ impl TuiCompleter {
                 name: "diagnostics".to_string(),
                 subcommands: vec![
                     "health".to_string(),
+                    "run".to_string(),
                     "status".to_string(),
+                    "ready".to_string(),
                     "metrics".to_string(),
                     "logs".to_string(),
                     "test".to_string(),
@@ -231,7 +431,9 @@ impl TuiCompleter {
                 name: "diag".to_string(), // Alias
                 subcommands: vec![
                     "health".to_string(),
+                    "run".to_string(),
                     "status".to_string(),
+                    "ready".to_string(),
                     "metrics".to_string(),
                     "logs".to_string(),
                     "test".to_string(),
@@ -243,6 +445,14 @@ impl TuiCompleter {
                 subcommands: vec![
                     "server".to_string(),
                     "overlay".to_string(),
+                    "shutdown".to_string(),
+                    "log".to_string(),
+                    "update".to_string(),
+                    "db".to_string(),
+                    "cert".to_string(),
+                    "service".to_string(),
+                    "install-service".to_string(),
+                    "uninstall-service".to_string(),
                 ],
                 description: "Process management".to_string(),
             },
@@ -254,14 +464,24 @@ impl TuiCompleter {
                     "commands".to_string(),
                     "redeems".to_string(),
                     "grpc".to_string(),
+                    "scenario".to_string(),
+                    "loadtest".to_string(),
                 ],
                 description: "Testing framework".to_string(),
             },
             CommandInfo {
                 name: "simulate".to_string(),
-                subcommands: vec![],
+                subcommands: vec![
+                    "list", "eventsub", "discord", "slash", "raid", "follow", "sub", "gift-subs",
+                    "hype-progress", "redeem", "cheer", "shoutout",
+                ].into_iter().map(String::from).collect(),
                 description: "Simulate events".to_string(),
             },
+            CommandInfo {
+                name: "watch".to_string(),
+                subcommands: vec!["status".to_string(), "osc".to_string(), "connections".to_string(), "events".to_string()],
+                description: "Live status dashboards and the event stream".to_string(),
+            },
             CommandInfo {
                 name: "osc".to_string(),
                 subcommands: vec![
@@ -270,9 +490,21 @@ impl TuiCompleter {
                     "status".to_string(),
                     "test".to_string(),
                     "toggle".to_string(),
+                    "pack".to_string(),
+                    "relay".to_string(),
+                    "midi".to_string(),
+                    "control".to_string(),
                 ],
                 description: "OSC control".to_string(),
             },
+            CommandInfo {
+                name: "pipeline".to_string(),
+                subcommands: vec![
+                    "list", "create", "import", "delete", "toggle", "enable", "disable",
+                    "show", "test", "throttle", "filter", "action", "history", "errors", "reload",
+                ].into_iter().map(String::from).collect(),
+                description: "Event pipeline management".to_string(),
+            },
         ]
     }
 }
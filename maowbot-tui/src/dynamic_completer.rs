@@ -54,7 +54,7 @@ impl DynamicCompleter {
             // Platform commands that need platform type completion
             (Some(&"platform"), Some(&"add")) => {
                 if parts.len() == 3 {
-                    let platforms = vec!["twitch", "discord", "vrchat"];
+                    let platforms = vec!["twitch", "discord", "vrchat", "kick"];
                     let prefix = parts[2];
                     candidates.extend(platforms.into_iter()
                         .filter(|p| p.starts_with(prefix))
@@ -96,7 +96,7 @@ impl DynamicCompleter {
             // Connection commands
             (Some(&"connection"), Some(&"start" | &"stop")) => {
                 if parts.len() == 3 {
-                    let platforms = vec!["twitch-irc", "twitch-eventsub", "discord", "vrchat"];
+                    let platforms = vec!["twitch-irc", "twitch-eventsub", "discord", "vrchat", "kick"];
                     let prefix = parts[2];
                     candidates.extend(platforms.into_iter()
                         .filter(|p| p.starts_with(prefix))
@@ -152,6 +152,7 @@ impl DynamicCompleter {
                 filter: None,
                 order_by: String::new(),
                 descending: false,
+                read_mask: None,
             };
             
             match client.user.clone().list_users(request).await {
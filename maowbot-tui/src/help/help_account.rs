@@ -3,12 +3,17 @@ pub const ACCOUNT_HELP_TEXT: &str = r#"Account Command:
   Manages user credentials for a given platform.
 
 Subcommands:
-  account add <platform> <desired_global_username>
+  account add <platform> <desired_global_username> [--device]
       1. Prompts if it's a bot account or not
       2. Finds or creates a DB user with the given global username
       3. Begins the auth flow (OAuth or API key, etc.)
       4. Stores the resulting credentials for that user+platform
 
+      --device  Use the device-code login instead of a browser callback on
+                port 9876. Shows a URL and a short code to enter from any
+                device, which suits headless servers. Supported for Twitch
+                (twitch-helix, twitch-irc); Discord has no device-code grant.
+
   account remove <platform> <usernameOrUUID>
       Revokes and removes stored credentials for the given user on a platform.
       <usernameOrUUID> can be either the user's name (global_username) or their DB UUID.
@@ -20,9 +25,23 @@ Subcommands:
       Shows detailed info about the credential record (tokens, expiration, etc.).
       <usernameOrUUID> can be either the user's name or their DB UUID.
 
+  account scopes [--recheck]
+      Shows which stored Twitch tokens lack scopes the bot now asks for, the
+      features that are degraded because of it, and the command that fixes
+      each one. Checked at startup; --recheck asks Twitch again.
+
+  account reauth <platform> <usernameOrUUID> [--device]
+      Logs an existing account in again so its token is granted the current
+      scopes, keeping its bot/broadcaster/teammate flags. Re-authing
+      twitch-eventsub redoes the Helix login it shares. <usernameOrUUID> may
+      also be the Twitch login name.
+
 Usage Examples:
   account add twitch testUser
+  account add twitch-irc myBot --device
   account remove discord testUser
   account list twitch
   account show twitch testUser
+  account scopes --recheck
+  account reauth twitch myChannel
 "#;
@@ -47,6 +47,23 @@ Examples:
   ai configure anthropic --api-key sk-ant-...     # Configure Anthropic
   ai chat "Hello, how are you?"                  # Test chat
 
+Answer safety (askai / askmao redeems):
+  Answers are filtered before they reach chat or TTS. Set with 'config set <key> <value>':
+  ai.safety.redact_pii                 Replace emails, phone/card numbers and IPs (default true)
+  ai.safety.profanity_words            Comma-separated words to mask
+  ai.safety.family_friendly_channels   Channels that also mask common swears and withhold
+                                       answers that need many masks (* for all)
+  ai.safety.withheld_message           What's said instead of a withheld answer
+  ai.safety.max_chunks                 Chat messages per answer, marked (1/3) … (default 4);
+                                       viewers type !continue for the next one
+  ai.safety.tts_max_chars              Characters passed to TTS (default 300)
+
+Channel memory:
+  ai.summary.enabled                   Summarize each channel's chat into the AI's memory so it
+                                       remembers earlier in a long stream (default false)
+  ai.summary.interval_minutes          Minutes between summaries (default 15)
+  ai.summary.min_messages              New lines a channel needs before it's summarized (default 20)
+
 Notes:
   - API keys are stored securely and only shown masked (last 4 chars visible)
   - You must configure at least one provider before using AI features
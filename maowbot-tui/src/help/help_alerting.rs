@@ -0,0 +1,67 @@
+// File: maowbot-tui/src/help/help_alerting.rs
+//
+// Detailed help text for the "alerting" command group.
+
+pub const ALERTING_HELP_TEXT: &str = r#"Alerting Command:
+  Alerts for operational problems, each with a severity (info, warning,
+  critical):
+    platform     a connection keeps reconnecting (warning) or gave up
+                 (critical); reconnecting afterwards is info
+    credential   a token or VRChat session could not be renewed (critical
+                 when it expires within the hour)
+    pipeline     many pipeline executions failed recently
+    disk         little free disk space left
+    moderation   the escalation ladder timed out or banned a repeat offender
+                 (warning; see automod pardon)
+
+  Every alert goes through every rule. A rule that matches its kind and
+  severity sends it to its route:
+    dm        a Discord DM to the user id given with --to
+    chat      a Twitch chat message in the channel given with --to, or the
+              broadcaster's channel
+    desktop   a notification in the GUI
+  A rule doesn't send the same alert again within its dedupe window, and
+  during its quiet hours only critical alerts get through. Every alert is
+  also published as the ops.alert event for pipelines.
+
+Usage:
+
+  alerting rules
+    Lists the rules.
+
+  alerting add <name> <dm|chat|desktop> [--to TARGET] [--kinds K,K]
+               [--min SEVERITY] [--dedupe SECS] [--quiet HH:MM-HH:MM]
+    Adds a rule. Kinds default to all, the minimum severity to warning and
+    the dedupe window to 900 seconds.
+
+  alerting set <name> [--route R] [--to TARGET|none] [--kinds K,K|all]
+               [--min SEVERITY] [--dedupe SECS] [--quiet HH:MM-HH:MM|none]
+    Changes a rule.
+
+  alerting remove <name>
+  alerting enable <name>
+  alerting disable <name>
+
+  alerting recent [count]
+    Alerts raised since the server started, newest first, with what each
+    rule did (sent, deduped, quiet or failed).
+
+  alerting test [kind] [severity]
+    Raises a test alert, skipping dedupe and quiet hours.
+
+Settings (config set <key> <value>):
+  alerting.enabled                  raise alerts at all (true)
+  alerting.timezone                 time zone of quiet hours (UTC)
+  alerting.discord_account          Discord account sending DMs (the bot credential)
+  alerting.reconnect_attempts       reconnects before a disconnect alert (3)
+  alerting.pipeline_window_minutes  window for failed executions (15)
+  alerting.pipeline_failures        failures in the window that alert (10, 0 = off)
+  alerting.disk_min_free_mb         free space that alerts (2048, 0 = off)
+  alerting.disk_path                directory whose disk is watched (working directory)
+
+Examples:
+  alerting add me dm --to 123456789012345678 --min warning --quiet 23:00-08:00
+  alerting add mods chat --kinds platform,credential --min critical
+  alerting set desktop --dedupe 300
+  alerting test disk critical
+"#;
@@ -0,0 +1,25 @@
+pub const ALIAS_HELP_TEXT: &str = r#"Alias Command:
+  Define shortcuts that expand into one or more TUI commands.
+
+Usage:
+  alias <name> = <command>[; <command>...]
+      Add or replace an alias (same as 'alias add')
+  alias add <name> [=] <command>[; <command>...]
+  alias remove <name>
+  alias show <name>
+  alias list
+
+Arguments:
+  Text typed after an alias name is passed to its expansion. Use $1..$9 for
+  individual arguments and $* for all of them; if the expansion has no
+  placeholders, the arguments are appended to its last command.
+
+Aliases are stored in ~/.maowbot_tui_aliases (one 'name = command' per line)
+and may refer to other aliases. Built-in command names cannot be redefined.
+In scripts, quote expansions that contain ';' so the line is not split.
+
+Examples:
+  alias brb = connection stop twitch-irc mybot; osc set AFK true
+  alias say = twitch msg $*
+  alias remove brb
+"#;
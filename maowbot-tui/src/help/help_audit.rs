@@ -0,0 +1,27 @@
+// File: maowbot-tui/src/help/help_audit.rs
+//
+// Detailed help text for the "audit" command group.
+
+pub const AUDIT_HELP_TEXT: &str = r#"Audit Command:
+  Shows who changed what. Every gRPC call that needs more than read
+  permission is recorded once it finishes, with the client (API token name),
+  the time and how the call ended. Config, command, credential, plugin and
+  moderation changes also record what they changed with the value before
+  and after (secret config values show as <redacted>).
+
+Usage:
+
+  audit list [options]  (or: audit l)
+    Lists the most recent changes first. Options:
+      --limit N          how many (default 50)
+      --since <age>      only the last 30m, 12h, 7d, ...
+      --by <client>      only calls from this API token
+      --method <name>    only this method, e.g. SetConfig
+      --target <text>    only changes to matching targets, e.g. config:chat
+      --all              include denied calls and older access records
+
+Examples:
+  audit list --since 1d
+  audit list --target command:twitch --limit 10
+  audit list --by stream-deck --all
+"#;
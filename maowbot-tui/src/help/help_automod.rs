@@ -0,0 +1,81 @@
+// File: maowbot-tui/src/help/help_automod.rs
+//
+// Detailed help text for the "automod" command group.
+
+pub const AUTOMOD_HELP_TEXT: &str = r#"Automod Command:
+  Moderation rules for the broadcaster's Twitch chat. Every chat line is
+  checked against the enabled rules; when several match, the harshest one is
+  enforced (ban over timeout over delete) and its hit counter goes up.
+
+  Rule kinds:
+    link    any link whose domain isn't in the pattern's allowlist; the pattern
+            lists allowed domains separated by commas or spaces (subdomains
+            count, e.g. "youtube.com" also allows m.youtube.com). Without a
+            pattern every link matches.
+    phrase  one or more phrases separated by "|", matched as whole words,
+            ignoring case
+    regex   a regular expression, ignoring case unless it starts with (?-i)
+
+  Severities: delete (remove the message), timeout (--timeout seconds,
+  600 by default), ban. Subs, VIPs and mods can be permitted past a rule;
+  mods are permitted unless --permit says otherwise. The broadcaster is never
+  matched.
+
+  Escalation: a rule with a --category ignores its severity and escalates
+  instead. A chatter's offenses in the category are counted across streams
+  (moderation.escalation_window_days) and each new one gets the next step of
+  moderation.escalation_ladder: a warning (message removed) first, then a 10
+  minute timeout, then a ban by default. Rules can share a category. Timeouts
+  and bans on the ladder raise a "moderation" alert for the mods, and a pardon
+  starts the ladder over.
+
+Usage:
+
+  automod list
+    Lists every rule with its action, permits and hit counter.
+
+  automod add <name> <link|phrase|regex> [pattern...] [--severity S]
+              [--timeout SECS] [--permit subs,vips,mods|none] [--category C]
+    Adds a rule. The name is shown to the chatter as the reason.
+
+  automod edit <name> [pattern...] [--severity S] [--timeout SECS] [--permit LIST]
+              [--category C|none]
+    Changes a rule. Words after the name replace the pattern; --permit
+    replaces the whole permit list; --category none stops escalating.
+
+  automod remove <name>
+  automod enable <name>
+  automod disable <name>
+  automod reset <name>
+    Zeroes the rule's hit counter.
+
+  automod test [--as sub|vip|mod] <message...>
+    Shows which rules a message would break and what would happen, without
+    acting on it.
+
+  automod offenses <user>
+    Shows the user's offenses per category and what the next one would get.
+    The user is a UUID or global username.
+
+  automod pardon <user> [category] [--lift]
+    Starts the ladder over in the category, or in every category. --lift
+    also lifts the user's Twitch ban or timeout.
+
+Settings (config set <key> <value>):
+  moderation.enabled        enforce the rules (true)
+  moderation.warn_in_chat   tell the chatter which rule they broke (true)
+  moderation.escalation_ladder        steps for repeat offenses
+                                      ("warn, timeout:600, ban")
+  moderation.escalation_window_days   days an offense counts, 0 = forever (90)
+
+  The broadcaster account needs the moderator:manage:chat_messages scope to
+  delete messages; re-authenticate it if it was set up before it was added.
+
+Examples:
+  automod add links link youtube.com, clips.twitch.tv --permit subs,mods
+  automod add spam phrase buy followers|cheap viewers --severity ban
+  automod add caps regex (?-i)[A-Z ]{25,} --severity timeout --timeout 60
+  automod test --as sub check out evil.xyz
+  automod edit spam --category spam
+  automod pardon somechatter spam --lift
+"#;
@@ -0,0 +1,51 @@
+// File: maowbot-tui/src/help/help_chatlog.rs
+//
+// Detailed help text for the "chatlog" command group.
+
+pub const CHATLOG_HELP_TEXT: &str = r#"Chatlog Command:
+  Search the chat archive: every chat message the bot has seen is stored in
+  the database (not just the recent in-memory cache) and kept for a number of
+  days per channel.
+
+Usage:
+
+  chatlog search [text] [--user U] [--channel C] [--platform P]
+                 [--since T] [--until T] [--limit N] [--offset N]  (or: chatlog s)
+    Lists matching messages, newest first (25 per page by default).
+    text:       full-text search; words must all appear, "quoted phrases"
+                match in order, and -word excludes messages containing it
+    --user:     global username or user UUID
+    --channel:  channel name, with or without '#'
+    --since/--until: 7d, 12h, 30m (that long ago) or YYYY-MM-DD (UTC)
+
+  chatlog lastseen <user> [channel]
+    Shows the last message a user sent, optionally in one channel.
+    Viewers can ask the same in chat with the built-in !lastseen <user>.
+
+  chatlog retention  (or: chatlog retention list)
+    Shows the default retention and every per-channel override.
+    The default comes from the config key chat_logging.default_retention_days
+    (30 days if unset).
+
+  chatlog retention set <platform> <channel> <days>
+    Keeps messages from one channel for the given number of days.
+
+  chatlog retention clear <platform> <channel>
+    Returns the channel to the default retention.
+
+  chatlog activity [--platform P] [--channel C] [--since T] [--until T] [--peaks N]
+    Message counts per 5 minutes, one row per channel and hour with twelve
+    cells (" " none, then . : * # from quiet to busiest), followed by the N
+    busiest 5-minute buckets (default 5). Covers the last 24 hours unless
+    --since/--until are given; ranges are limited to 90 days. Counts are kept
+    when the messages themselves expire.
+
+  Expired messages are removed by the periodic maintenance task.
+
+Examples:
+  chatlog search raid --channel mychannel --since 7d
+  chatlog search "good morning" --user somebody
+  chatlog lastseen somebody #mychannel
+  chatlog retention set twitch-irc mychannel 90
+  chatlog activity --channel mychannel --since 3d --peaks 10
+"#;
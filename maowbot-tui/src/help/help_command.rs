@@ -2,7 +2,15 @@
 /// Detailed help text for the "command" group:
 ///
 ///   - command list [platform]
+///   - command stats <commandName> [platform] [days]
+///   - command import <nightbot|streamelements|fossabot> <file> [platform] [--dry-run]
+///   - command setresponse <commandName> <text...|none> [--platform P]
 ///   - command setcooldown <commandName> <seconds> [platform]
+///   - command setusercooldown <commandName> <seconds> [platform]
+///   - command setrolecooldown <commandName> <role> <seconds> [platform]
+///   - command setbypassmods <commandName> <true|false> [platform]
+///   - command setprivate <commandName> <true|false> [platform]
+///   - command setreply <commandName> <true|false> [platform]
 ///   - command setwarnonce <commandName> <true|false> [platform]
 ///   - command setrespond <commandName> <credentialId|username|none> [platform]
 ///   - command setplatform <commandName> <newPlatform> [oldPlatform]
@@ -24,21 +32,66 @@
 ///   command disable mycommand
 ///
 /// See below for details on each subcommand.
-
 pub const COMMAND_HELP_TEXT: &str = r#"Command Management:
 
 Subcommands:
 
-  command list [platform]
+  command list [platform] [--limit N] [--offset N]
     Lists all known commands. If a platform is given, only that platform’s commands are shown.
     Example: "command list twitch-irc"
 
+  command stats <commandName> [platform] [days]
+    Shows how often the command ran over the last N days (default 7), how many runs
+    failed and the last error, and how long its handler took (average, p50/p95/p99, max).
+    Example: "command stats !so twitch-irc 30"
+
+  command import <nightbot|streamelements|fossabot> <file> [platform] [--dry-run]
+    Creates text commands from another bot's export: JSON as the bot's API returns it
+    (a list, or {"commands": [...], "timers": [...]}) or CSV with a header row. Names,
+    responses, permission levels, cooldowns, aliases and enabled flags carry over;
+    $(user)/${user}, $(touser), $(channel) and $(query)/${1:} become {user}, {touser},
+    {channel} and {args}. Other variables stay in the text as they are and are listed,
+    with the commands using them. Commands whose name exists already or belongs to a
+    built-in are left alone. Timers are listed but not imported.
+    Roles aren't ranked here: "subscriber" lets subscribers in, not moderators or VIPs.
+    --dry-run:  only show what would be created
+    Example: "command import nightbot ./nightbot.json --dry-run"
+
+  command setresponse <commandName> <text...|none> [--platform P]
+    Sets the text a command without built-in logic answers with. {user}, {touser} (the first
+    argument, or the chatter), {channel} and {args} are filled in. "none" clears it.
+    Example: "command setresponse discord Join us at discord.gg/example, {user}!"
+
   command setcooldown <commandName> <seconds> [platform]
-    Sets the global cooldown (in seconds). During cooldown, re-use is blocked.
+    Sets the global cooldown (in seconds). During cooldown, re-use is blocked for everyone.
     Example: "command setcooldown !hello 5"
 
+  command setusercooldown <commandName> <seconds> [platform]
+    Sets the per-user cooldown: how long one chatter waits before using the command again.
+
+  command setrolecooldown <commandName> <role> <seconds> [platform]
+    Sets a cooldown shared by everyone holding a chat role (subscriber, vip, ...). A chatter
+    with several such roles waits for all of them. 0 removes the role's cooldown.
+    Example: "command setrolecooldown !hug subscriber 30"
+
+  command setbypassmods <commandName> <true|false> [platform]
+    If true, moderators and the broadcaster skip every cooldown of the command.
+
+    The global, per-user and role cooldowns are independent; a use must clear all of them.
+    How chatters hear about a running cooldown is set by commands.cooldown_feedback:
+    "whisper" (default; the Twitch account needs user:manage:whispers), "chat" or "silent".
+
+  command setprivate <commandName> <true|false> [platform]
+    If true, the command's reply is whispered to the chatter instead of posted in chat
+    (Twitch only; needs user:manage:whispers on the bot or broadcaster account).
+
+  command setreply <commandName> <true|false> [platform]
+    If true, the command answers as a reply to the message that triggered it: a reply
+    thread on Twitch, a message reference on Discord. Other platforms post normally.
+
   command setwarnonce <commandName> <true|false> [platform]
-    If set to true, the first user attempt during cooldown gets a warning. If false, no cooldown messages.
+    If true, a chatter is told about a cooldown only on their first blocked attempt; later
+    attempts during the same cooldown are ignored. If false, every attempt gets the notice.
 
   command setrespond <commandName> <credentialId|username|none> [platform]
     Specifies the credential used for responding. E.g. if "myBotUser" is a known Twitch-IRC account,
@@ -57,7 +110,13 @@ Subcommands:
 Examples:
   command list
   command list twitch-irc
+  command import streamelements ./se-commands.json --dry-run
+  command setresponse lurk {user} is lurking, enjoy!
   command setcooldown !shout 10
+  command setusercooldown !hug 60
+  command setbypassmods !hug true
+  command setprivate !points true
+  command setreply !roll true
   command setwarnonce !hello false
   command setrespond !roll kittyn twitch-irc
   command setplatform !ping vrchat twitch-irc
@@ -10,17 +10,31 @@ Usage:
   config
     Shows usage for the config command (this text).
 
-  config list  (or: config l)
+  config list [--limit N] [--offset N]  (or: config l)
     Lists all key-value pairs from the bot_config table.
 
   config get <key>  (or: config g <key>)
     Gets the value for a specific key.
 
   config set <key> <value>  (or: config s <key> <value>)
-    Inserts or updates the given key with the provided value.
+    Inserts or updates the given key with the provided value. Settings the
+    server knows (see `config schema`) are checked first, e.g. a number out
+    of range is rejected. Most take effect immediately; those marked
+    "restart" are only read at startup.
+
+  config set-secret <key> <value>
+    Stores a secret (e.g. a webhook token) encrypted with the master key.
+    Pipeline http_request actions read it as {secret:<key>}. Secrets are not
+    shown by list/get/export; set-secret again to replace one, or delete it.
 
   config delete <key>  (or: config d <key>)
     Removes the specified key (and its value) from the bot_config table.
+    Known settings fall back to their default.
+
+  config schema [category]
+    Lists every setting the server reads, grouped by category (general,
+    twitch, vrchat, osc, chat_logging, chat_cache), with its type, allowed
+    range, default, and current value.
 
   config export [filename]
     Exports all configuration to a JSON file.
@@ -31,14 +45,48 @@ Usage:
     Without --merge: Replaces all existing configs.
     With --merge: Only adds new keys, preserves existing ones.
 
+  config rotate-key [--dry-run] [--yes]
+    Generates a new master encryption key and re-encrypts every stored
+    credential (platform tokens, AI API keys, OBS passwords, config secrets)
+    with it, in a single transaction. Asks for confirmation unless --yes is given.
+    --dry-run: Checks that every secret decrypts with the current key,
+               without changing anything.
+    The key lives in the OS keyring, or in <config dir>/maowbot/master.key
+    when no keyring is available; the previous key is kept alongside it
+    (master-key.previous / master.key.previous). The key file is encrypted
+    with $MAOWBOT_KEYFILE_PASSPHRASE when that is set; otherwise it is plain
+    base64 protected only by its 0600 permissions. A key supplied through
+    $MAOWBOT_MASTER_KEY cannot be rotated from here.
+
+  config export-all [filename]
+    Writes a full backup of the database to one JSON archive: workspaces,
+    users and their platform identities, credentials, platform client
+    settings, bot config, commands, redeems and event pipelines. The archive
+    is tagged with the schema version it was taken at.
+    Default filename: maowbot_backup_<timestamp>.json
+    Credentials, client secrets and config secrets are sealed with a new
+    backup key that is printed once. Keep it with the archive; without it
+    they cannot be restored.
+    Not included: API tokens, chat/usage history, analytics and the audit log.
+
+  config import-all <filename> [--key <backup-key>] [--yes]
+    Restores an archive from export-all, e.g. on a fresh install. Entries that
+    already exist (same id or name) are kept; config values are overwritten.
+    Without --key, everything except credentials and secrets is restored.
+    Archives from a newer schema version are refused; upgrade MaowBot first.
+
 Examples:
   config l
   config g callback_port
   config s ttv_broadcaster_channel #mychannel
   config delete callback_port
+  config schema chat_logging
   config export my_config_backup.json
   config import my_config_backup.json
   config import new_settings.json --merge
+  config rotate-key --dry-run
+  config export-all maowbot_backup.json
+  config import-all maowbot_backup.json --key mwbk_...
 
 Export File Format:
   {
@@ -31,6 +31,18 @@ Subcommands:
   connection chat off
       Disables all chat display in the TUI.
 
+  connection identity list [platform]
+      Shows which account speaks in which channel.
+
+  connection identity set <platform> <account> [channel <name> | command <name>]
+      Makes <account> the one that speaks. Without a target it becomes the
+      platform default; with 'channel' it speaks only in that channel; with
+      'command' it answers only that command. The account must be added
+      (account add) and connected.
+
+  connection identity clear <platform> [channel <name> | command <name>]
+      Removes the assignment again.
+
 Examples:
   connection start twitch kittyn
   connection stop discord
@@ -39,6 +51,13 @@ Examples:
   connection autostart list
   connection chat on twitch
   connection chat off
+  connection identity set twitch-irc mainbot
+  connection identity set twitch-irc brandbot channel brandchannel
+  connection identity set twitch-irc brandbot command !lurk
+
+Which account replies, first match wins: the command's own account, the
+channel's account, the platform default, the bot account, the broadcaster.
+Channel assignments are kept in the 'chat.identities' setting.
 
 Note: The legacy commands 'start', 'stop', 'autostart', and 'chat' 
       are still supported but using 'connection' is recommended.
@@ -3,7 +3,7 @@ pub const CREDENTIAL_HELP_TEXT: &str = r#"Credential Command:
   Direct management of platform credentials (OAuth tokens, API keys, etc.).
 
 Subcommands:
-  credential list [platform]
+  credential list [platform] [--limit N] [--offset N]
       Lists all stored credentials, optionally filtered by platform.
       Shows credential status (Active, Expired, etc.) and user roles.
 
@@ -15,9 +15,12 @@ Subcommands:
       Revokes a credential locally. With --platform-revoke, also
       revokes the token at the platform's OAuth endpoint.
 
-  credential health [platform]
-      Shows health statistics for credentials, including expiration
-      status and refresh timestamps. Can filter by platform.
+  credential health [platform] [--hours N] [--no-check]
+      Validates every stored credential against its platform and lists
+      each one with its validity, expiry countdown, scopes and last
+      refresh time. Credentials expiring within 72 hours (or N hours
+      with --hours) are marked with ⚠. Use --no-check to skip the
+      platform calls and report stored expiry only.
 
   credential batch-refresh <platform> [--force]
       Refreshes all credentials for a specific platform.
@@ -29,6 +32,7 @@ Platforms:
   - twitch-eventsub
   - discord
   - vrchat
+  - kick
 
 Examples:
   credential list
@@ -36,6 +40,7 @@ Examples:
   credential refresh 123e4567-e89b-12d3-a456-426614174000
   credential revoke 123e4567-e89b-12d3-a456-426614174000 --platform-revoke
   credential health
+  credential health twitch --hours 24
   credential batch-refresh twitch --force
 
 Note: For adding new credentials, use the 'account add' command instead.
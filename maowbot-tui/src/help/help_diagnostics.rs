@@ -10,18 +10,41 @@ Subcommands:
       - Active platform runtimes
       - Overall system status
 
+  diagnostics run [database|credentials|routing|eventsub|osc|vrchat|obs|disk ...]
+      Runs the server's dependency checks (all of them, or just the ones named) and
+      lists each as OK / WARN / FAIL / SKIP with the reason:
+      - database: Postgres round trip and latency
+      - credentials: expiry of every stored token; Twitch tokens are validated live
+        and checked for scopes the bot now asks for
+      - routing: which Twitch account (bot or broadcaster) each operation runs as
+        under twitch.account_routing, e.g.
+        config set twitch.account_routing {"moderation": "broadcaster", "whispers": "bot_only"}
+        Ads, markers, raids, redeems and stream_info always use the broadcaster.
+      - eventsub: the broadcaster's EventSub subscriptions are enabled
+      - osc: the OSC server is running and has seen VRChat
+      - vrchat: each VRChat account's session
+      - obs: each configured OBS instance is connected
+      - disk: free space where the bot and its database live
+
   diagnostics status
       Shows detailed status information for all components:
       - Plugin details with versions and authors
       - Runtime statistics for each platform
       - Connection states and uptime
 
+  diagnostics ready
+      Shows how far server startup got. Subsystems start in dependency order
+      (database, repositories, auth, platforms, plugins, grpc), each as pending,
+      starting, ready, degraded (started with errors) or failed, with how long it
+      took. Until all of them are up every other command gets "server is still
+      starting"; start the TUI with --wait-ready [SECS] to wait for that instead.
+
   diagnostics metrics
-      Display system performance metrics (not yet implemented):
-      - Message throughput
-      - Command processing times
-      - Memory usage
-      - Error rates
+      Shows runtime counters since startup:
+      - Events published and OSC packets
+      - Outbound chat guard: messages sent, repeats dropped (outbound.dedupe_seconds),
+        messages over the per-channel rate (outbound.max_messages per
+        outbound.window_seconds) and messages from the bot's own accounts left unanswered
 
   diagnostics logs tail [lines]
       Show the last N lines of logs (default: 50).
@@ -43,7 +66,10 @@ Subcommands:
 
 Examples:
   diagnostics health
+  diagnostics run
+  diag run credentials eventsub
   diagnostics status
+  diagnostics ready
   diagnostics test
   diagnostics logs tail 100
   diagnostics logs search "error"
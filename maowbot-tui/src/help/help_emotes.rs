@@ -0,0 +1,37 @@
+// File: maowbot-tui/src/help/help_emotes.rs
+//
+// Detailed help text for the "emotes" command group.
+
+pub const EMOTES_HELP_TEXT: &str = r#"Emotes Command:
+Emote usage counted in Twitch chat: Twitch emotes, plus 7TV, BTTV and FFZ
+  emotes of each channel (emotes.third_party). The last hour is kept minute by
+  minute; older usage is stored as daily totals. Chatters can ask the same with
+  !emotestats [window].
+
+Usage:
+
+  emotes top [window] [channel] [limit]
+    Lists the most used emotes. The window is a rolling 1-60 minutes
+    ("15m", "1h", the default) or whole days in UTC ("today", "7d").
+    The channel defaults to the broadcaster's.
+
+  emotes sets [channel]
+    Shows the 7TV, BTTV and FFZ emotes the bot has for a channel. Sets are
+    refetched every emotes.refresh_minutes, and 7TV changes arrive live.
+
+  emotes refresh [channel]
+    Refetches a channel's emote sets now.
+
+Settings:
+  emotes.tracking_enabled   Count emotes at all (default true)
+  emotes.third_party        Fetch 7TV, BTTV and FFZ emotes (default true)
+  emotes.channels           Channels kept synced (blank for the broadcaster's)
+  emotes.refresh_minutes    Minutes between refetches (default 30)
+
+Examples:
+  emotes top
+  emotes top 15m
+  emotes top 7d somechannel 25
+  emotes sets
+  emotes refresh somechannel
+"#;
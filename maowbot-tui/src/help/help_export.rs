@@ -0,0 +1,34 @@
+// File: maowbot-tui/src/help/help_export.rs
+//
+// Detailed help text for the "export" command.
+
+pub const EXPORT_HELP_TEXT: &str = r#"Export Command:
+  Writes chat messages, events or command uses from a time range to a CSV or
+  JSON Lines file, for spreadsheets and analysis tools. Rows come oldest
+  first and are streamed to the file as they arrive, with a running count.
+
+Usage:
+
+  export <chat|events|commands> <file> [--format csv|jsonl] [--since T] [--until T]
+         [--platform P] [--channel C]
+    chat:       archived chat messages (message_id, timestamp, platform,
+                channel, user_id, username, message)
+    events:     the event journal (event_id, timestamp, event_type, kind,
+                platform, channel, data as JSON)
+    commands:   command uses (usage_id, timestamp, platform, channel,
+                command, user_id, input, duration_ms, error)
+    --format:   csv or jsonl; by default .jsonl/.json files get JSON Lines
+                and anything else CSV
+    --since/--until: 7d, 12h, 30m (that long ago) or YYYY-MM-DD (UTC);
+                the default range is the last 7 days
+    --channel:  channel name, with or without '#'
+
+  The file is overwritten. If the export stops partway, what was written so
+  far stays in the file and the row count is reported. Exports need an
+  admin token.
+
+Examples:
+  export chat chat.csv --since 30d --channel mychannel
+  export events events.jsonl --since 2026-01-01 --until 2026-02-01
+  export commands commands.csv --platform twitch-irc
+"#;
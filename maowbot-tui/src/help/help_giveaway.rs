@@ -0,0 +1,57 @@
+// File: maowbot-tui/src/help/help_giveaway.rs
+//
+// Detailed help text for the "giveaway" command group.
+
+pub const GIVEAWAY_HELP_TEXT: &str = r#"Giveaway Command:
+  Run giveaways on the broadcaster's Twitch channel. Viewers enter by typing
+  the keyword in chat, by redeeming a channel point reward the bot creates for
+  the giveaway, or either. Giveaways and entries are stored in the database,
+  so a restart keeps them.
+
+Usage:
+
+  giveaway open <title...> [--keyword K] [--points N] [--channel C]
+                [--sub-only] [--follow-days N] [--exclude-winners DAYS] [--sub-weight N]
+    Opens a giveaway. At least one of --keyword or --points is required.
+    --keyword:          chat message that enters the giveaway (e.g. !enter)
+    --points:           creates a channel point reward costing N points;
+                        redemptions that are not eligible are refunded
+    --channel:          chat channel for keyword entry (defaults to the broadcaster)
+    --sub-only:         only subscribers may enter
+    --follow-days:      viewers must have followed for at least N days
+    --exclude-winners:  skip anyone who won a giveaway in the last DAYS days
+    --sub-weight:       subscribers get N tickets instead of 1
+
+  giveaway list
+    Lists the 20 most recent giveaways, newest first.
+
+  giveaway show [id]
+    Shows a giveaway and all of its entries.
+
+  giveaway close [id]
+    Stops taking entries; winners can still be drawn.
+
+  giveaway cancel [id]
+    Stops taking entries and prevents any draw. Points already spent are
+    not refunded.
+
+  giveaway draw [id] [count]
+    Draws count winners (default 1), closing the giveaway first if needed.
+
+  giveaway reroll [id] [username]
+    Replaces a winner (the latest one if no username) with a new draw.
+
+  [id] may be the full id, its first few characters as shown by "list",
+  or left out to use the most recent giveaway.
+
+  Announcements go to chat (config key giveaways.announce_chat), to Discord
+  when giveaways.discord_account, giveaways.discord_guild_id and
+  giveaways.discord_channel_id are set, and to overlays as a "giveaway" event.
+
+Examples:
+  giveaway open Steam key --keyword !enter --follow-days 7 --sub-weight 2
+  giveaway open Merch bundle --points 5000 --exclude-winners 30
+  giveaway draw
+  giveaway draw 3f2a9c1e 2
+  giveaway reroll 3f2a9c1e somebody
+"#;
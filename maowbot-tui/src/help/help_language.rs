@@ -0,0 +1,44 @@
+// File: maowbot-tui/src/help/help_language.rs
+//
+// Detailed help text for the "language" command group.
+
+pub const LANGUAGE_HELP_TEXT: &str = r#"Language Command:
+  Bot responses (built-in commands, command errors, giveaway, protection and
+  moderation alerts) are looked up by key in each channel's language. Text
+  stored with this command wins over the bundled language packs; missing keys
+  fall back to the base language ("pt" for "pt-br") and then English.
+  Templates use {placeholders} from the English text.
+
+Usage:
+
+  language list
+    Lists languages with how many keys each translates, and the channels
+    that have their own language.
+
+  language channel <channel> <language|default> [platform]
+    Sets the language a channel is answered in. "default" returns it to
+    i18n.default_language. The platform defaults to twitch-irc.
+
+  language keys [language] [missing]
+    Lists every key with the text used in the language and where it comes
+    from (custom, bundled or fallback). "missing" lists only fallbacks.
+
+  language set <language> <key> <template...>
+    Stores custom text for one key.
+
+  language unset <language> <key>
+    Removes custom text, so the bundled or fallback text is used again.
+
+  language import <language> <file.json>
+    Stores every key of a JSON object of key => template, e.g. a copy of
+    a bundled pack. Nothing is stored if a key or placeholder is unknown.
+
+Settings:
+  i18n.default_language   Language of channels without their own (default en)
+
+Examples:
+  language channel somechannel es
+  language keys de missing
+  language set es ping.reply ¡Pong!
+  language import fr ./fr.json
+"#;
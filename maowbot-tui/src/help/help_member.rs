@@ -9,7 +9,6 @@
 ///
 /// This file is referenced by the help system (help::mod.rs) so that
 /// typing `help member` will display the usage details below.
-
 pub const MEMBER_HELP_TEXT: &str = r#"Member Command:
   Provides management and insights into bot members (also called "users" in some contexts),
   focusing on chat logs, merges, and specialized data that extends beyond simple user info.
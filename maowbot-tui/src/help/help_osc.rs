@@ -24,6 +24,65 @@ OSC Toggle Management:
   osc toggle delete      Delete a trigger
   osc toggle active      Show currently active toggles
 
+Drip Packs:
+  osc pack export <file> <name...>
+                         Save every toggle with its redeem (cost, duration, prompt,
+                         color, icon) as a shareable JSON pack
+  osc pack import <file> Match the pack's parameters to the current avatar, confirm
+                         or rename each one, then create the redeems and toggles.
+                         Redeems with the same name are reused
+
+Chatbox Relay:
+  osc relay status       Show the relay settings
+  osc relay on|off       Relay selected Twitch chat lines to the VRChat chatbox,
+                         for when you're in VR without the overlay
+  osc relay filters <list>
+                         Which lines: mentions (your name, with or without @),
+                         mods (moderator messages), highlighted (highlight-my-message
+                         redeems), or all. Comma-separated
+  osc relay channels <list|all>
+                         Only relay these Twitch channels (default: all joined)
+  osc relay interval <seconds>
+                         Minimum time each line stays up (default 3); long lines
+                         stay long enough to read
+  osc relay queue <n>    Lines kept waiting (default 5); when chat is faster than
+                         the chatbox the oldest are dropped, and lines waiting over
+                         30 seconds are skipped
+  osc relay names <n>    Longest name shown before it's cut off (default 12)
+
+MIDI Controllers:
+  osc midi device <name|off>
+                         Read the MIDI input port whose name contains <name>
+                         (e.g. nanoKONTROL2); works on Linux, macOS and Windows
+  osc midi list          Show mappings and whether the device is open
+  osc midi learn <name> <action...>
+                         Wait 15 seconds for a key, pad, knob or button and map it
+  osc midi add <name> <note|cc> <channel> <number> <action...>
+                         Map a note or controller by number (channels 1-16)
+  osc midi on|off <name> Enable or disable a mapping
+  osc midi delete <name> Remove a mapping
+  Actions:
+    osc <parameter> [bool|int|float]
+                         bool follows the key (CCs count as pressed from 64 up),
+                         int is the velocity or value 0-127, float is 0-1
+    scene <scene name...>
+                         Switch OBS to the scene when pressed
+    chat <text...>       Send the text to the broadcaster's Twitch channel when
+                         pressed (at most once every midi.chat_cooldown_seconds)
+
+Chat Controls:
+  osc control list       Show what chat can drive with !toggle and !set
+  osc control add <name> <parameter> <bool|int|float> [options]
+                         Add a control, or replace the one with that name:
+                         --role <everyone|subscriber|vip|moderator|broadcaster>
+                         (default moderator), --min/--max <n> to clamp values
+                         (default: VRChat's 0-255 for ints, -1 to 1 for floats),
+                         --cooldown <secs>, --off to add it disabled
+  osc control delete <name>
+                         Remove a control
+  In chat, !toggle <name> flips a bool control and !set <name> <value> sets any
+  control; both check the control's role and cooldown.
+
 OSC Destinations:
   osc set vrcdest        Set VRChat OSC destination (default: 127.0.0.1:9000)
   osc set robodest       Set Robot OSC destination
@@ -35,6 +94,15 @@ Examples:
   osc toggle create <redeem_id> /avatar/parameters/Ears bool true false 60
   osc toggle list                              # See all configured triggers
   osc set vrcdest 192.168.1.100:9000          # Change VRChat OSC destination
+  osc pack export hoodie.json Hoodie Pack     # Share your toggles
+  osc pack import hoodie.json                  # Import someone else's
+  osc relay filters mentions,highlighted       # Only mentions and highlights
+  osc relay on                                 # Start relaying chat
+  osc midi learn wings osc Wings               # Pad toggles the Wings parameter
+  osc midi learn brb scene BRB                 # Button switches OBS to BRB
+  osc midi add hype cc 1 20 chat HYPE HYPE     # CC 20 on channel 1 sends a macro
+  osc control add ears Ears bool --role vip    # VIPs can !toggle ears
+  osc control add tail TailSpeed float --min 0 --max 0.8 --cooldown 10
 
 Toggle Types:
   bool   - Boolean values (true/false)
@@ -43,7 +111,11 @@ Toggle Types:
 
 Notes:
 - OSC service must be running to send parameters or chatbox messages
+- Relayed lines are shown as "name: message", cut to the chatbox's 144 characters;
+  the bot's own messages and your own lines aren't relayed
 - Toggle durations are in seconds; omit for permanent toggles
 - Use 'osc raw' to debug incoming OSC messages from VRChat
 - Default VRChat OSC port is 9000, but can be changed in VRChat settings
+- Pack icons can't be uploaded through the Twitch API; set them on the dashboard
+- Without a Twitch broadcaster account, imported redeems are created by the next 'redeem sync to'
 "#;
\ No newline at end of file
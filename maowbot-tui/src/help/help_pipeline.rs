@@ -6,65 +6,144 @@ PIPELINE - Event Pipeline Management
   incoming events (chat messages, follows, etc.) through filters and actions.
 
 COMMANDS:
-  pipeline list [all]                   - List pipelines (include 'all' to show disabled)
+  pipeline list [all] [--limit N] [--offset N]
+                                        - List pipelines (include 'all' to show disabled)
   pipeline create <name> [description]  - Create a new pipeline
                   [priority]            
                   [stop_on_match]       
                   [stop_on_error]       
-  pipeline delete <id>                  - Delete a pipeline
-  pipeline toggle <id> <enabled|disabled> - Enable or disable a pipeline
-  pipeline show <id>                    - Show pipeline details with filters and actions
+  pipeline import <file>                - Create a pipeline with filters and actions
+                                          from a .toml or .json spec file
+  pipeline delete <pipeline>            - Delete a pipeline
+  pipeline enable <pipeline>            - Enable a pipeline
+  pipeline disable <pipeline>           - Disable a pipeline
+  pipeline toggle <pipeline> <enabled|disabled> - Enable or disable a pipeline
+  pipeline show <pipeline>              - Show pipeline details with filters and actions
+  pipeline test <pipeline> <event_type> [event_json]
+                                        - Run a synthetic event through one pipeline
+                                          (works on disabled pipelines too)
+  pipeline throttle <pipeline> [show|set <key=value>...|clear|reset]
+                                        - Show or change a pipeline's cooldowns and
+                                          run limits (see THROTTLING)
   pipeline reload                       - Reload all pipelines from database
 
+  <pipeline> may be a pipeline name or its ID.
+
 FILTER COMMANDS:
-  pipeline filter add <pipeline_id> <filter_type> [config_json] [order] [negated] [required]
+  pipeline filter add <pipeline> <filter_type> [config_json] [order] [negated] [required]
     - Add a filter to a pipeline
   
   pipeline filter remove <filter_id>
     - Remove a filter from a pipeline
   
-  pipeline filter list <pipeline_id>
+  pipeline filter list <pipeline>
     - List all filters for a pipeline
   
   pipeline filter types
     - Show available filter types
 
 ACTION COMMANDS:
-  pipeline action add <pipeline_id> <action_type> [config_json] [order] [continue_on_error] 
+  pipeline action add <pipeline> <action_type> [config_json] [order] [continue_on_error] 
                       [is_async] [timeout_ms] [retry_count] [retry_delay_ms]
     - Add an action to a pipeline
   
+  pipeline action condition <action_id> <condition>
+    - Run the action only when the condition holds: an expression over the
+      pipeline variables (e.g. bits >= 1000 && !is_gift), previous_success,
+      previous_failure, or none to always run it
+  
   pipeline action remove <action_id>
     - Remove an action from a pipeline
   
-  pipeline action list <pipeline_id>
+  pipeline action list <pipeline>
     - List all actions for a pipeline
   
   pipeline action types
     - Show available action types
 
 HISTORY COMMANDS:
-  pipeline history [pipeline_id] [limit] [offset]
+  pipeline history [pipeline] [limit] [offset]   (or --limit N --offset N)
     - Show execution history (optionally filtered by pipeline)
 
+  pipeline errors [pipeline] [limit]
+    - Show recent failed executions with their error messages
+
+SPEC FILES:
+  name = "welcome"
+  description = "Greet chatters"
+  priority = 50
+  enabled = true
+
+  [[filters]]
+  type = "platform_filter"
+  config = { platforms = ["twitch-irc"] }
+
+  [[actions]]
+  type = "twitch_message"
+  config = { message_template = "Welcome {user}!" }
+  condition = "message contains 'hello'"   # optional
+
+  [throttle]                               # optional
+  user_cooldown_seconds = 3600
+
+THROTTLING:
+  Settings for 'pipeline throttle <pipeline> set' (0 turns one off):
+    cooldown=<s>        seconds between two runs of the pipeline
+    user_cooldown=<s>   seconds between two runs for the same user
+    max=<n>             runs per window for the whole pipeline
+    max_per_user=<n>    runs per window for each user
+    window=<s>          the window for max and max_per_user (default 60)
+  e.g. pipeline throttle hydrate set user_cooldown=300 max=10 window=60
+  Runs are counted in the database, so restarts don't reset the limits;
+  'reset' forgets the counted runs. Throttled events are logged with
+  "Throttled: ..." in 'pipeline history'. 'pipeline test' ignores limits.
+
+VARIABLES AND CONDITIONS:
+  Every run starts with variables from the event: event_type, platform, user,
+  channel, message, bits, amount, tier, reward, viewers, ... Actions can use
+  them as {name} and add their own (http_request keeps its response).
+  Expressions compare them: == != > >= < <= contains, joined with && (and),
+  || (or), ! (not) and parentheses, e.g. if bits >= 1000 && tier == 3000.
+
+  Tiered responses in one pipeline (action order, type, config):
+    10 branch          {"if": "bits >= 1000", "then": 30}
+    20 twitch_message  {"message_template": "Thanks for the bits, {user}!"}
+    25 stop
+    30 twitch_message  {"message_template": "WOW, {bits} bits! Thank you {user}!"}
+  The "stop" action ends a pipeline; a branch's "then"/"else" is an action
+  order to jump ahead to, "stop" or "next". expression_filter {"if": "..."}
+  rejects the event for the whole pipeline instead.
+
+TEST EVENT TYPES:
+  chat_message  - fields: platform, channel, user, text
+  tick, system_message
+  any Twitch EventSub type, e.g. channel.follow, channel.subscribe
+
 EXAMPLES:
   # Create a pipeline for welcoming new users
   pipeline create "Welcome Message" "Welcomes new chatters" 100 true false
   
   # Add a filter to check if it's a chat message event
-  pipeline filter add <pipeline_id> "event_type_filter" "{\"event_types\": [\"chat.message\"]}"
+  pipeline filter add <pipeline> "event_type_filter" "{\"event_types\": [\"chat.message\"]}"
   
   # Add a filter to check if user is new
-  pipeline filter add <pipeline_id> "user_first_message" "{\"within_minutes\": 60}"
+  pipeline filter add <pipeline> "user_first_message" "{\"within_minutes\": 60}"
   
   # Add an action to send a welcome message
-  pipeline action add <pipeline_id> "twitch_message" "{\"message_template\": \"Welcome {{user.display_name}} to the stream!\"}"
+  pipeline action add <pipeline> "twitch_message" "{\"message_template\": \"Welcome {{user.display_name}} to the stream!\"}"
+  
+  # Whisper the reply to the chatter instead (needs user:manage:whispers); also
+  # answers incoming whispers when the user.whisper.message subscription is granted
+  pipeline action add <pipeline> "twitch_message" "{\"account\": \"mybot\", \"message_template\": \"You're in the queue, {user}!\", \"reply_privately\": true}"
   
   # Enable the pipeline
   pipeline toggle <pipeline_id> enabled
   
+  # Try it without waiting for a real chatter
+  pipeline test welcome chat_message {"user": "newviewer", "text": "hi"}
+  
   # View execution history
-  pipeline history <pipeline_id> 20
+  pipeline history welcome 20
 
 NOTES:
   - Pipelines are processed in priority order (lower numbers first)
@@ -7,6 +7,8 @@ Subcommands:
       Prompts for client_id and client_secret if needed, then stores them.
       If <platformName> is "twitch", we also store "twitch-irc" and "twitch-eventsub"
       configurations in one go, reusing the same client_id/client_secret.
+      For "kick", register http://localhost:9876/callback as the app's redirect URL
+      at https://kick.com/settings/developer, then `account add kick <name>`.
 
   platform remove <platformName>
       Removes the DB record for that platform's config. Also removes "twitch-irc" and
@@ -20,6 +22,7 @@ Subcommands:
 
 Usage Examples:
   platform add twitch
+  platform add kick
   platform remove twitch
   platform list
   platform show discord
@@ -0,0 +1,67 @@
+// File: maowbot-tui/src/help/help_protect.rs
+//
+// Detailed help text for the "protect" command group.
+
+pub const PROTECT_HELP_TEXT: &str = r#"Protect Command:
+  Raid defense for the broadcaster's Twitch chat. When auto-detection is on,
+  the last few seconds of chat are checked every second; if the message rate,
+  the number of chatters sending the same text, or the share of brand-new
+  accounts gets too high, chat is locked down: follower-only mode and slow mode
+  are switched on (and Shield Mode, if configured). Every lockdown is logged as
+  an incident with the chat messages captured while it lasted.
+
+Usage:
+
+  protect status
+    Shows whether auto-detection is on, the Shield Mode state and any
+    running lockdown.
+
+  protect shield <on|off>
+    Turns Twitch Shield Mode on or off right away.
+
+  protect lockdown [reason...]
+    Locks chat down by hand, the same way a detected spike would.
+
+  protect lift
+    Ends the running lockdown and restores the previous chat settings.
+    Lockdowns also lift themselves after protection.calm_minutes without
+    a new spike (0 = only by hand).
+
+  protect incidents
+    Lists the 20 most recent incidents, newest first.
+
+  protect incident <id>
+    Shows one incident and the messages captured during it. The id may be
+    shortened to its first few characters as shown by "incidents".
+
+Settings (config set <key> <value>):
+  protection.enabled              turn auto-detection on (off by default)
+  protection.window_secs          seconds of chat checked (10)
+  protection.messages_per_sec     average rate that counts as a spike (10)
+  protection.identical_messages   chatters sending the same text (8)
+  protection.new_account_days     accounts younger than this are new (7)
+  protection.new_account_percent  share of new accounts, needs at least
+                                  protection.min_chatters chatters (60%, 10)
+  protection.likely_bot_percent   share of scored chatters that look like bots,
+                                  same minimum (0 = off)
+  protection.likely_bot_score     bot score in percent that counts as a bot (70)
+  protection.followers_only       follower-only mode during a lockdown (true)
+  protection.follower_only_minutes  required follow age in minutes (10)
+  protection.slow_mode_secs       slow mode delay, 0 = leave it alone (10)
+  protection.shield_mode          also turn on Shield Mode (false)
+  protection.announce             tell chat when a lockdown starts/ends (true)
+
+  Bot scores come from bot detection, which scores new chatters once they've
+  sent bot_detection.sample_messages messages (3) or after two minutes; set
+  bot_detection.enabled to false to turn it off. Chatters first seen within
+  bot_detection.new_chatter_hours (24) count as new.
+
+  The broadcaster account needs the moderator:manage:shield_mode and
+  moderator:manage:chat_settings scopes; re-authenticate it if it was set
+  up before these were added.
+
+Examples:
+  protect lockdown follow-bot wave
+  protect shield off
+  protect incident 3f2a9c1e
+"#;
@@ -22,7 +22,7 @@ Usage:
 
 Subcommands:
 
-  redeem list
+  redeem list [--limit N] [--offset N]
     Lists all known channel-point redeems in the DB for the default platform ("twitch-eventsub").
 
   redeem enable <redeemName>
@@ -57,6 +57,42 @@ Subcommands:
     Removes the redeem from the database. The <accountName> parameter is for tracking which account
     is requesting removal; currently not used except for display.
 
+  redeem sync [both|to|from]
+    Compares the bot's redeems with the broadcaster's custom rewards on Twitch, shows what differs
+    and asks before applying:
+      both (default)  Import rewards the bot doesn't know, push bot-managed rewards to Twitch and
+                      copy everything else from Twitch.
+      to              Only create or update bot-managed rewards on Twitch.
+      from            Only import rewards and copy their settings from Twitch.
+    Redeems whose reward was re-created on Twitch are relinked by title. Redeems whose reward was
+    deleted on Twitch are reported but left as they are.
+
+  redeem schedule list
+    Lists scheduled redeems with their base and current cost, and every schedule ('*' = applies now).
+
+  redeem schedule add <name> <redeemName> when <condition> then <action> [priority N]
+    Changes a bot-managed reward while the condition holds. Conditions:
+      time [days] HH:MM-HH:MM [timezone]   e.g. "time mon-fri 18:00-22:00 Europe/Berlin"
+      category <name>                      while the stream category matches
+      hype                                 while a hype train runs
+    Actions: enable, disable, cost <N>, multiply <X>. Schedules apply lowest priority first, so a
+    higher priority wins. The redeem's own cost and enabled state are used when none applies.
+    Redeem sync leaves the cost and enabled state of scheduled redeems to their schedules.
+
+  redeem schedule remove|enable|disable <name> <redeemName>
+    Removes or toggles a schedule.
+
+  redeem setapproval <redeemName> <true|false>
+    When true, redemptions don't run right away but wait in the approval queue. Denied
+    redemptions, and those left waiting longer than redeems.approval_timeout_seconds, are
+    refunded. Refunds need a reward that doesn't skip Twitch's request queue.
+
+  redeem queue
+    Lists redemptions waiting for approval, oldest first.
+
+  redeem approve|deny <queueNumber|approvalId>
+    Runs a queued redemption, or drops it and refunds the viewer's points.
+
 Examples:
 
   redeem list
@@ -68,6 +104,13 @@ Examples:
   redeem setcommand "!mycmd" "Fancy Reward"
   redeem setaccount "KittyN" "Fancy Reward"
   redeem remove "KittyN" "Fancy Reward"
+  redeem sync
+  redeem sync from
+  redeem schedule add evenings Hydrate when time mon-fri 18:00-23:00 Europe/Berlin then enable
+  redeem schedule add hype2x Song Request when hype then multiply 2 priority 10
+  redeem schedule add artcheap Sketch when category Art then cost 50
+  redeem setapproval Avatar Swap true
+  redeem approve 1
 
 Notes:
   - The code examples assume "twitch-eventsub" as the primary platform for channel point redeems.
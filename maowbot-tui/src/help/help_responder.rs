@@ -0,0 +1,53 @@
+// File: maowbot-tui/src/help/help_responder.rs
+//
+// Detailed help text for the "responder" command group.
+
+pub const RESPONDER_HELP_TEXT: &str = r#"Responder Command:
+  Chat responders answer lines that match a pattern, without a command
+  prefix. Lines starting with "!" are left to commands. When a line matches
+  several responders, the first one (in creation order) that is off cooldown
+  and wins its chance roll answers; its hit counter goes up and the
+  responder.triggered event is published for pipelines.
+
+  Match kinds:
+    regex   a regular expression, ignoring case unless it starts with (?-i)
+    any     keywords separated by "|"; any one of them, as a whole word
+    all     keywords separated by "|"; every one of them, in any order
+
+  Responses are templates with {user}, {channel} and {match}. With several
+  --say options one is picked at random; with none the responder only
+  publishes its event.
+
+Usage:
+
+  responder list
+    Lists every responder with its responses, chance, cooldowns and hits.
+
+  responder add <name> <regex|any|all> <pattern...> [--say TEXT...]...
+                [--platform twitch-irc|discord|kick] [--channel C]
+                [--chance PCT] [--cooldown SECS] [--user-cooldown SECS]
+    Adds a responder. Chance defaults to 100%, the cooldown to 30 seconds
+    and the per-user cooldown to none. Without --platform/--channel it
+    answers everywhere.
+
+  responder edit <name> [pattern...] [--match K] [--say TEXT...]... [options]
+    Changes a responder. Words after the name replace the pattern, --say
+    replaces every response, and "any" clears --platform or --channel.
+
+  responder remove <name>
+  responder enable <name>
+  responder disable <name>
+
+  responder test [--platform P] [--channel C] <message...>
+    Shows which responders a message would trigger, ignoring chance and
+    cooldowns, without answering.
+
+Settings (config set <key> <value>):
+  responders.enabled   answer matching chat lines (true)
+
+Examples:
+  responder add greet any hello|hi|o7 --say hi {user}! --say o7 {user} --chance 50
+  responder add lurk all going|lurk --say enjoy the lurk {user} --user-cooldown 600
+  responder add when regex ^when (is|does) the stream --say Check !schedule
+  responder test --platform discord hello everyone
+"#;
@@ -0,0 +1,27 @@
+pub const SCRIPT_HELP_TEXT: &str = r#"Script / Batch Mode:
+  Run TUI commands without the interactive prompt, e.g. for setup automation
+  or CI smoke tests against a running server.
+
+Usage:
+  tui-grpc exec "<cmd>; <cmd>; ..."
+  tui-grpc --script <file>
+
+Options:
+  --json            Print one JSON object per command:
+                      {"line":1,"command":"...","success":true,"output":"..."}
+                    followed by a final {"summary":{...}} object
+  --keep-going      Continue after a failed command (default: stop at the first failure)
+  --no-autostart    Connect to an already-running server instead of starting one
+  --server-url URL  Server to connect to (with --no-autostart)
+
+Script files:
+  - One command per line; ';' also separates commands on a line
+  - Blank lines and lines starting with '#' are ignored
+  - Commands that ask for confirmation (e.g. 'pipeline delete') read it from stdin
+
+Exit status is 1 if any command failed, 0 otherwise. Only command output is
+written to stdout; connection progress and logs go to stderr.
+
+Example:
+  tui-grpc --no-autostart --json exec "platform list; pipeline list all"
+"#;
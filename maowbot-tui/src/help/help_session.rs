@@ -0,0 +1,26 @@
+// File: maowbot-tui/src/help/help_session.rs
+//
+// Detailed help text for the "session" command group.
+
+pub const SESSION_HELP_TEXT: &str = r#"Session Command:
+  Stream sessions: one per Twitch broadcast, opened at stream.online and
+  closed at stream.offline. Each keeps running totals (chat messages, unique
+  chatters, follows, subs, gifted subs, bits, incoming raids, redemptions,
+  hype moments, peak viewers) and per-chatter message counts. Markers placed
+  during the broadcast belong to its session. Counts are written every
+  minute; a broadcast already live when the server starts keeps its session.
+
+Usage:
+
+  session list [count]
+    Lists the newest sessions (default 10) with their length and chat counts.
+
+  session show [current|last|<session id>]
+    Shows one session's stats, markers and top chatters. "current" (the
+    default) is the live broadcast; "last" is the live one or the last to end.
+
+Examples:
+  session list
+  session show
+  session show last
+"#;
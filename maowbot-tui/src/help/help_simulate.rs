@@ -2,43 +2,56 @@ pub fn help_simulate() -> String {
     r#"
 === Simulate Command ===
 
-Trigger simulated events through the bot's Twitch IRC connection.
-This allows testing commands and functionality without waiting for
-actual Twitch events. Requires an active Twitch connection.
+Publish synthetic platform events as if they had arrived live. EventSub
+notifications are built from a realistic sample, run through the same parser
+as the websocket connection and published on the event bus, so pipelines,
+redeem handlers and plugins react to them normally. No stream is required.
 
 Usage: simulate <type> [args...]
 
 Types:
-  chat <account> <channel> <message>
-    Send a chat message to a channel
-    Example: simulate chat bot #mychannel "Hello world!"
+  list
+    Show every event type that can be simulated, with its shortcuts
 
-  command <account> <channel> <command> [args...]
-    Trigger a command in a channel
+  eventsub <event_type> [overrides_json]
+  <shortcut|event_type> [overrides_json]
+    Publish an EventSub notification. <event_type> is the full subscription
+    type (e.g. channel.hype_train.progress) or a shortcut:
+      online, offline, update, follow, ad, bits, cheer, raid, sub, resub,
+      gift (gift-subs), ban, unban, hype-begin, hype-progress, hype-end,
+      shoutout, redeem, auto-redeem
     Examples:
-      simulate command bot #mychannel ping
-      simulate command bot #mychannel so @coolstreamer
-      simulate command bot #mychannel vanish @lurker
+      simulate raid
+      simulate raid {"viewers": 500, "from_broadcaster_user_login": "coolstreamer"}
+      simulate gift-subs {"total": 20, "tier": "2000"}
+      simulate hype-progress {"level": 4, "last_contribution": {"total": 1000}}
+      simulate redeem {"reward": {"title": "Be Cute"}, "user_input": "hi"}
+      simulate eventsub channel.chat.notification {"notice_type": "raid"}
 
-  redeem <account> <channel> <redeem_name> [input]
-    Simulate a channel points redeem (via test command)
+  discord [message] [overrides_json]
+    Feed a Discord chat message through the message service
+    Fields: channel, user_id, username, text, user_roles, guild_id
     Examples:
-      simulate redeem bot #mychannel "Be Cute"
-      simulate redeem bot #mychannel "TTS" "Read this message!"
+      simulate discord {"text": "!ping"}
+      simulate discord message {"channel": "bot-spam", "text": "hello"}
 
-  scenario <account> <channel> <type>
-    Run pre-built test scenarios:
-    - spam: Send multiple messages quickly
-    - commands: Test various commands
-    - mixed: Mix of messages and commands
-    Example: simulate scenario bot #mychannel mixed
+  slash <command> [option=value ...]
+  discord slash [overrides_json]
+    Run a built-in command as a Discord slash command and print its answer.
+    Options are parsed with the same schema as chat arguments
+    Fields: command, options, channel, user_id, username, user_roles
+    Examples:
+      simulate slash lastseen user=bob
+      simulate slash set name=ears value=1
+      simulate discord slash {"command": "vrchat", "options": {"status": "online"}, "user_roles": ["moderator"]}
 
-Important Notes:
-- <account> is the bot account name to send from
-- <channel> must include the # prefix (e.g., #mychannel)
-- The bot must be connected to Twitch and joined to the channel
-- Commands will be processed by the bot's normal command handlers
-- Messages appear in chat as if sent by the specified account
+Notes:
+- Overrides are deep-merged into the sample event: nested objects keep the
+  fields you do not mention
+- An override with the wrong field type is rejected, exactly as a malformed
+  live event would be
+- The published event body is printed so it can be copied into
+  'pipeline test'
 "#
     .to_string()
-}
\ No newline at end of file
+}
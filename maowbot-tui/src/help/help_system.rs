@@ -7,6 +7,23 @@ Manage MaowBot server and overlay processes.
 Usage:
   system [process] [command]
   system shutdown [reason] [grace_period_seconds]
+  system log [levels]
+  system log set <module> <level>
+  system log reset <module>
+  system update [status|check]
+  system update apply [restart_delay_seconds]
+  system update auto <on|off>
+  system db [status]
+  system db snapshot [label]
+  system db compact [--full]
+  system db check
+  system db retention [--apply]
+  system cert [show]
+  system cert regenerate [san ...]
+  system cert issue <name> [out_dir]
+  system install-service [--system [--user <account>]] [--db <url>]
+  system uninstall-service
+  system service <start|stop|status>
 
 Processes:
   server    - The MaowBot gRPC server
@@ -18,6 +35,58 @@ Commands:
   status    - Check if the process is running
   shutdown  - Request graceful server shutdown (via gRPC)
 
+Logging:
+  log [levels]               - Show the log format and active filter
+  log set <module> <level>   - Change a module's level without restarting
+                               (off, error, warn, info, debug, trace)
+  log reset <module>         - Remove a module override
+
+Updates:
+  update [status]            - Show the running version and the last check
+  update check               - Read the release feed now
+  update apply [delay]       - Download, verify and install the latest release,
+                               then restart after <delay> seconds (default 10)
+  update auto <on|off>       - Install new releases on the check schedule
+                               (updater.check_interval_hours, default 24)
+
+Database:
+  db [status]                - Size, WAL, snapshots and the last integrity report
+  db snapshot [label]        - pg_dump the database into postgres/snapshots
+  db compact [--full]        - CHECKPOINT + VACUUM ANALYZE and rotate server.log;
+                               --full rewrites tables (locks them while it runs)
+  db check                   - Re-run the integrity checks
+  db retention [--apply]     - Show how many old rows the retention limits would delete
+                               from chat, the event journal, command usage and AI
+                               memories; --apply deletes them now
+  Snapshots are also taken every db.snapshot_interval_hours (default 24) and before
+  migrations; db.snapshot_keep (default 7) limits how many are kept. Compaction runs
+  every db.compact_interval_hours (default 168).
+  Retention limits are the retention.* settings (chat age follows each channel's
+  chatlog retention); they are applied with the biweekly maintenance.
+
+Certificates:
+  cert [show]                - Names the gRPC certificate covers, its fingerprint
+                               and whether clients must present a certificate
+  cert regenerate [san ...]  - Re-issue the server certificate from the local CA
+                               (certs/ca.crt). It always covers localhost, this
+                               machine's host name and LAN addresses; extra names
+                               replace tls.extra_sans. Takes effect after a restart.
+  cert issue <name> [dir]    - Issue a client certificate and write client.crt,
+                               client.key and ca.crt to <dir> (default certs/clients/<name>)
+  Set tls.require_client_cert to true (and restart) to require client certificates.
+  Processes started next to the server use certs/client.crt automatically; remote
+  ones read MAOWBOT_GRPC_CA, MAOWBOT_GRPC_CLIENT_CERT and MAOWBOT_GRPC_CLIENT_KEY.
+
+Boot service:
+  install-service            - Register maowbot-server to start at boot, running in
+                               the current directory (where certs/ and postgres/ live).
+                               Linux: a systemd user unit with lingering enabled;
+                               --system writes /etc/systemd/system instead (needs root)
+                               and runs as the user who ran sudo, or --user <account>.
+                               Windows: a Service Control Manager entry (needs Administrator).
+  uninstall-service          - Stop and remove the service
+  service <start|stop|status> - Control the installed service
+
 Examples:
   system server status                    # Check if server is running
   system overlay start                    # Start the overlay
@@ -25,10 +94,26 @@ Examples:
   system server                           # Show server status (shorthand)
   system shutdown                         # Shutdown server with 30s grace period
   system shutdown "maintenance" 60        # Shutdown for maintenance in 60 seconds
+  system log set maowbot_core::platforms debug
+  system log reset maowbot_core::platforms
+  system update check
+  system update apply 30                  # Update, restart in 30 seconds
+  system install-service --db postgres://maow@localhost:5432/maowbot
+  system service status
+  system db snapshot before-upgrade
+  system cert regenerate maowbot.lan 203.0.113.7
+  system cert issue living-room-pc
 
 Note: 
 - The TUI automatically starts the server if it's not running when you launch it.
 - The 'shutdown' command requires an active gRPC connection to the server.
 - The 'stop' command forcefully terminates the process, while 'shutdown' is graceful.
+- Start the server with --log-format json for one structured JSON object per line
+  (event_type, platform, user and latency_ms fields where available).
+- Updates need updater.feed_url (https) and updater.public_key (base64 Ed25519).
+  Each build is signed together with its version and target, and one whose
+  SHA-256 or signature doesn't match is never installed. The previous binary
+  is kept next to the new one with an .old suffix, and platform connections that
+  were running are started again after the restart.
 "#
 }
\ No newline at end of file
@@ -13,11 +13,71 @@ Subcommands:
   commands   - Run command processing tests  
   redeems    - Run redeem processing tests
   grpc       - Run gRPC mock tests
+  scenario [--live] <file|dir>...
+             - Run scenario files (.toml or .json) and report pass/fail
+  loadtest [--rate N] [--duration SECS] [--users N] [--channel C] [--platform P]
+             - Send synthetic chat through the server's message service and
+               report latency and drops (see Load Test below)
 
 Examples:
   test_harness run-all     # Run all tests
   test_harness twitch      # Test Twitch functionality
   test_harness commands    # Test command handling
+  test_harness scenario scenarios/          # Every scenario in a directory
+  test_harness scenario --live raid.toml    # Against the connected server
+  test_harness loadtest --rate 500 --duration 30
+
+Scenario Files:
+A scenario lists fixtures, steps and expectations. Without --live it runs
+against a fresh in-memory context; with --live each step is sent to the
+server as a TUI command and expectations are polled until timeout_ms.
+
+  name = "Raid thank-you"
+  timeout_ms = 5000
+
+  [fixtures]                      # in-memory only
+  users = ["viewer"]              # viewer, moderator, vip, subscriber, broadcaster
+  commands = ["ping"]             # ping, followage, vanish, so
+  redeems = ["osc_toggle"]        # cute, tts, osc_toggle
+
+  [[steps]]
+  action = "simulate"             # chat, redeem, simulate, osc, command, wait
+  event = "raid"
+  overrides = { viewers = 50 }
+
+  [[steps]]
+  action = "command"              # live only: any TUI command
+  line = "pipeline history 5"
+
+  [[expect]]
+  type = "output"                 # live only
+  command = "pipeline history 5"
+  contains = "Raid"
+
+Expectation types:
+  chat_sent { contains }              in-memory
+  command_executed { command, user }  in-memory
+  redeem_executed { redeem, user }    in-memory
+  osc_param { name, value }           in-memory
+  db_rows { table, contains, min }    both (users, commands, redeems; in-memory
+                                      also chat_messages, command_usage,
+                                      redeem_usage; live also pipelines,
+                                      pipeline_executions)
+  output { command, contains, not_contains }  live
+
+Load Test:
+Messages go through the same path as real chat: they land in the chat cache
+and are published on the event bus, where pipelines see them too. Senders
+(loadtest-0, loadtest-1, ...) are throwaway users that aren't saved, the chat
+log and responders skip the messages, and the channel is cleared from the
+cache afterwards. Channels with real chat are refused. Defaults: 100 msg/s
+for 10s from 50 users to twitch-irc #maowbot-loadtest; at most 10000 msg/s,
+600s and 1000 users.
+
+  Ingest       time of each message service call
+  End to end   from the call until the event bus delivered the message
+  Dropped      messages whose event never arrived
+  Sent rate    falls below --rate when the server can't keep up
 
 The test harness includes:
 - Mock gRPC client for simulating server responses
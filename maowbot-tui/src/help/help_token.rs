@@ -0,0 +1,66 @@
+// File: maowbot-tui/src/help/help_token.rs
+//
+// Detailed help text for the "token" command group.
+
+pub const TOKEN_HELP_TEXT: &str = r#"Token Command:
+  Manage the API tokens gRPC clients use to talk to the server. Every token
+  is bound to a role, and each service method requires a permission:
+
+    admin      everything: config, credentials, plugins, tokens, shutdown
+    moderator  read access, plus chat moderation and content (commands,
+               redeems, pipelines, OBS scenes, OSC/VRChat actions)
+    readonly   read access only, for dashboards
+
+Usage:
+
+  token list [--all]  (or: token l)
+    Lists active tokens with their role and last use. --all includes
+    revoked tokens.
+
+  token create <name> <admin|moderator|readonly> [--workspace <name>]
+    Creates a token and prints it once. The server only stores a hash, so
+    copy it right away. With --workspace the token can only use that
+    workspace: requests without a workspace go to it, and requests for any
+    other workspace are refused. Admin tokens can't be limited.
+
+  token revoke <name>
+    Rejects the token from now on. You cannot revoke the token your own
+    session is using.
+
+  token audit [--limit N]
+    Shows the most recent N (default 50) audit log entries: every call that
+    needs more than read permission, and every denied call. 'audit list'
+    filters them and shows what each call changed.
+
+Notes:
+  - Clients send the token as "authorization: Bearer <token>" metadata.
+  - On first start the server writes an admin token named 'local-admin' to
+    <config dir>/maowbot/admin.token (readable only by you). The TUI, GUI
+    and overlay on the same machine pick it up automatically; elsewhere set
+    MAOWBOT_API_TOKEN.
+  - Deleting admin.token and restarting the server issues a new local token.
+  - Plugins connecting over the plugin stream keep using the plugin
+    passphrase instead.
+
+HTTP Control API (Stream Deck and similar tools):
+  Turn it on with "config set control_api.enabled true" and restart; it
+  listens on control_api.address (default 127.0.0.1:7717, plain HTTP).
+  Send a moderator token as "Authorization: Bearer <token>", or as
+  ?token=<token> for tools that can't set headers. Actions need POST:
+
+    GET  /api/control/v1/actions                      list actions (readonly is enough)
+    POST /api/control/v1/scene?scene=Live&instance=1  switch the OBS scene
+    POST /api/control/v1/osc-toggle?parameter=Ears    flip a bool avatar parameter
+    POST /api/control/v1/alert-test?type=raid         fire a simulated alert
+    POST /api/control/v1/mute-alerts?muted=true       mute alerts (no value = toggle)
+
+  Parameters can also be sent as a JSON object body. Muted alerts still
+  reach overlays and the GUI, marked "muted" so they show without a sound.
+
+Examples:
+  token create stream-deck moderator
+  token create obs-dashboard readonly
+  token create kitty-mods moderator --workspace kitty
+  token revoke stream-deck
+  token audit --limit 20
+"#;
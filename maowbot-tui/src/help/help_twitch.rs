@@ -25,6 +25,36 @@ Subcommands:
   twitch default <channelName>
       Sets the channel that will be automatically joined on restart (stored in bot_config).
 
+  twitch marker [description]
+      Places a stream marker at the current point of the live broadcast. Markers are also
+      placed automatically on raids, big redeems and OBS scene changes (see the markers.*
+      settings), and chat can use !marker.
+
+  twitch chapters [streamId|videoId]
+      Without an id, lists the chapter lists exported when past streams ended.
+      With an id, prints that stream's chapters ("0:00 Start" lines) ready to paste into a VOD.
+
+  twitch schedule [refresh]
+      Lists the upcoming segments of the Twitch stream schedule, with their ids.
+  twitch schedule add <YYYY-MM-DD> <HH:MM> <minutes> [weekly] <title...>
+      Adds a segment starting at the given UTC time; "weekly" makes it recurring.
+  twitch schedule title|category <segmentId> <text...>
+      Changes a segment's title or category (by category name).
+  twitch schedule cancel|restore <segmentId>
+      Cancels (or restores) the next occurrence of a segment.
+  twitch schedule remove <segmentId>
+      Deletes a segment, every occurrence for a weekly one.
+      Before a scheduled stream the bot can switch OBS to a starting-soon scene and
+      announce it in chat; see the twitch.schedule.* settings.
+
+  twitch eventsub [list]
+      Lists the EventSub subscriptions with their status and cost, the cost budget
+      Twitch allows, the subscriptions the bot needs but lacks, and the stale ones.
+  twitch eventsub repair
+      Deletes stale subscriptions (disabled, from an earlier session, for a channel
+      other than the broadcaster's, or no longer needed) and creates the missing ones,
+      stopping when the cost budget is used up. The same runs whenever EventSub connects.
+
 Usage Examples:
   twitch active kittyn
   twitch join coolchannel
@@ -32,4 +62,8 @@ Usage Examples:
   twitch msg #coolchannel Hello everyone!
   twitch chat
   twitch default #coolchannel
+  twitch marker Boss fight
+  twitch chapters 2085912345
+  twitch schedule add 2026-11-07 18:00 180 weekly Cozy Saturday
+  twitch eventsub repair
 "##;
@@ -16,12 +16,21 @@ Basic Operations:
       Shows detailed user information including platform identities and analysis.
 
   user list [pageSize] [pageNum]
+  user list --limit N [--offset N]
       Lists all users with pagination (default: 20 per page).
 
-  user search <query>
+  user search <query> [--limit N] [--offset N]
       Searches for users by username or UUID.
 
 Extended Operations:
+  user card <usernameOrUUID> [numMessages]
+      One view of everything known about a viewer: identities and roles on each platform,
+      Twitch follow date and sub tier, channel point redeems, notes, recent chat (default 10
+      messages) and what the AI remembers about them. New Twitch chatters also show a bot
+      score (0-1) with the signals behind it: account age, following right before chatting,
+      follow-bot advertising, links, repetitive or low-entropy messages.
+      Parts that couldn't be looked up (e.g. Twitch unreachable) are listed under "Incomplete".
+
   user chat <usernameOrUUID> [numMessages] [platform] [channel]
       View chat history for a user (not yet implemented).
 
@@ -30,6 +39,10 @@ Extended Operations:
 
   user merge <primaryUser> <secondaryUser>
       Merges two user accounts, combining their data.
+      Viewers can link their own Twitch and Discord accounts instead: "!link" in Twitch chat
+      whispers a code (or DM "!link" to the Discord bot), and sending "!link CODE" from the
+      other platform within 10 minutes merges both into the older user record.
+      Turn this off with "config set commands.link_enabled false".
 
   user roles add <username> <role>
       Adds a role to a user.
@@ -44,14 +57,31 @@ Extended Operations:
       Shows detailed analytics for a user including message stats,
       command usage, and activity patterns.
 
+  user analyze --all [--days N]
+      Recomputes every user's analysis scores from their archived chat
+      (only the last N days with --days) in the background, a batch of
+      users at a time. Progress is posted as system messages.
+
+  user analyze --resume
+      Continues the latest cancelled or interrupted run from its checkpoint,
+      e.g. after a restart.
+
+  user analyze status
+      Shows how far the latest run got.
+
+  user analyze cancel
+      Stops the running recompute after its current batch.
+
 Examples:
   user add newuser123
   user info kittyn
+  user card kittyn 20
   user search kitt
   user note kittyn "Regular viewer, likes cats"
   user merge kittyn kittyn_alt
   user roles add kittyn moderator
   user analysis 550e8400-e29b-41d4-a716-446655440000
+  user analyze --all --days 30
 
 Note: The 'member' command has been deprecated and merged into this command.
       All member functionality is now available through the user command.
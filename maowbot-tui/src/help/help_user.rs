@@ -15,7 +15,8 @@ Subcommands:
       Accepts either a UUID or a username.
 
   user info <usernameOrUUID>
-      Displays details for that user (created_at, last_seen, etc.).
+      Displays details for that user (created_at, last_seen, etc.), plus the
+      most recent moderator notes and moderation actions.
       Accepts either a UUID or a username.
 
   user search <query>
@@ -26,6 +27,17 @@ Subcommands:
       - If 'p' is provided (e.g. `user list p 50`), lists in pages with an optional page size (default=25).
       - Press ENTER after each page to continue.
 
+  user note <usernameOrUUID> <note text...>
+      Attaches a timestamped note, credited to the API token in use.
+  user note list <usernameOrUUID> [limit]
+  user note delete <noteId>
+
+  user history <usernameOrUUID> [limit]
+      Shows timeouts, bans, unbans and warnings across all platforms, newest first.
+      Twitch bans and timeouts are recorded automatically from EventSub.
+  user history add <usernameOrUUID> <timeout|ban|unban|warning> <platform> [--channel C] [--duration S] [reason...]
+      Records an action by hand (e.g. a warning given on Discord or in VRChat).
+
 Usage Examples:
   user add MyCoolUser
   user remove MyCoolUser
@@ -37,4 +49,7 @@ Usage Examples:
   user list
   user list p
   user list p 50
+  user note MyCoolUser Warned about spoilers in VC
+  user history MyCoolUser
+  user history add MyCoolUser warning discord --channel general spoilers
 "#;
\ No newline at end of file
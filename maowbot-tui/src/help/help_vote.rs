@@ -0,0 +1,41 @@
+// File: maowbot-tui/src/help/help_vote.rs
+//
+// Detailed help text for the "vote" command group.
+
+pub const VOTE_HELP_TEXT: &str = r#"Vote Command:
+  Crowd control: chat votes which OSC effect happens next. Viewers type an
+  option's number (#1, #2, ...) or its name; voting again changes their vote.
+  When the time is up the option with the most vote weight wins (ties are
+  drawn at random), its OSC chat control is toggled and a crowd.vote_decided
+  event goes out for pipelines. Only one vote per channel can be open.
+
+Usage:
+
+  vote start <seconds> <title...> | <label>[=control] | <label>[=control] ... [--channel C]
+    Opens a vote lasting 5 to 600 seconds with 2 to 9 options, separated by
+    '|'. "=control" names an OSC chat control (see "osc control list"),
+    which must be an enabled on/off toggle; options without one only
+    trigger pipelines.
+    --channel:  chat channel to vote in (defaults to the broadcaster)
+
+  vote status [--channel C]
+    Shows the open vote's live tallies, or the result of the last vote.
+
+  vote end [--channel C]
+    Decides the open vote now.
+
+  vote cancel [--channel C]
+    Ends the open vote without a winner.
+
+  Vote weight: every vote counts 1, plus 1 per crowd.weight_hours hours the
+  viewer has watched the channel, up to crowd.max_weight. Set
+  crowd.weight_hours to 0 to count every vote once.
+
+  Announcements go to chat (config key crowd.announce_chat) and to overlays
+  as a "crowd_vote" event, which also carries the live tallies.
+
+Examples:
+  vote start 60 Next effect | Confetti=confetti | Spin=spin | Cat ears=ears
+  vote status
+  vote end
+"#;
@@ -32,12 +32,52 @@ Subcommands:
       Sets the default VRChat account for built-in commands (e.g. !world, !instance, !avatar).
       The specified accountName must correspond to a VRChat account registered within the bot's database.
 
+  vrchat here [refresh]
+      Lists the friends in the streamer's current instance, as of the last presence poll
+      (every vrchat.presence.poll_seconds); 'refresh' polls VRChat right away. VRChat only reports
+      where friends are, so other players in the instance aren't listed. Friends on
+      vrchat.presence.watch_friends are marked with * and, when they join, switch on the
+      vrchat.presence.osc_parameter avatar parameter (and greet them in the chatbox if
+      vrchat.presence.chatbox is on). Joins and leaves are also pipeline triggers
+      (vrchat.friend_joined / vrchat.friend_left), and chat can ask with !whosHere.
+
+  vrchat session [check]
+      Shows whether each stored VRChat login still works, as of the last check. The bot checks every
+      vrchat.session_check_minutes (default 30), which also keeps live sessions from expiring. With
+      'check' VRChat is asked right away. An expired login, or one where VRChat wants a 2FA code again,
+      is reported once as a credential alert.
+
+  vrchat login [accountName]
+      Logs an expired VRChat account back in. Asks for the password and, if VRChat wants one, the 2FA
+      code; the account keeps its user and roles. accountName may be left out when only one VRChat
+      account is stored.
+
+  vrchat group [requests [refresh]]
+      Lists pending join requests for the group set in vrchat.group.id, as of the last poll (every
+      vrchat.group.poll_minutes); 'refresh' asks VRChat right away. New requests are pipeline
+      triggers (vrchat.group_join_request). The active VRChat account needs permission to manage
+      the group's members.
+
+  vrchat group approve|reject <usr_id or display name>
+      Answers a join request.
+
+  vrchat group post [--silent] <title> | <text>
+      Posts an announcement to the group and notifies its members (not with --silent). With
+      vrchat.group.announce_live on, the bot also posts vrchat.group.announce_title/announce_text
+      when the stream goes live ({user}, {title}, {category} and {link} are filled in).
+
 Usage Examples:
   vrchat world
   vrchat avatar
   vrchat avatar change 1234567890abcdef
   vrchat instance
   vrchat account kittyn
+  vrchat here
+  vrchat session check
+  vrchat login kittyn
+  vrchat group requests refresh
+  vrchat group approve Mochi
+  vrchat group post Movie night | Join us at 8pm in the theater world!
 
 Notes:
   • Ensure your VRChat credentials are correctly configured in the system before using these commands.
@@ -0,0 +1,25 @@
+pub const WATCH_HELP_TEXT: &str = r#"Watch Command:
+  Refresh a compact dashboard in place until Ctrl+C is pressed.
+
+Usage:
+  watch status [interval_secs] [--count N]
+      Uptime, events/sec, OSC packets/sec, disconnected accounts, top event types
+  watch osc [interval_secs] [--count N]
+      OSC state and packets in/out per second
+  watch connections [interval_secs] [--count N]
+      Connection state of every platform account
+  watch events [chat,alert,connection,osc] [--count N]
+      Print the server's event stream as events arrive (all kinds by default).
+      If the connection drops it reconnects and first prints what was missed,
+      marked "(missed)"; "[gap]" means some events were too old to replay.
+
+Options:
+  interval_secs   Seconds between refreshes (default 2)
+  --count N       Stop after N samples; useful from scripts, where each sample
+                  is printed as a separate frame instead of redrawing
+
+The event stream is the same one the GUI and overlay follow (SubscribeEvents).
+
+Rates are computed from the difference between two samples, so the first
+frame shows '-/s'.
+"#;
@@ -0,0 +1,60 @@
+// File: maowbot-tui/src/help/help_workspace.rs
+//
+// Detailed help text for the "workspace" command group.
+
+pub const WORKSPACE_HELP_TEXT: &str = r#"Workspace Command:
+  Run several broadcasters' channels from one server. Each workspace has its
+  own commands, redeems, config values and platform accounts.
+
+Usage:
+
+  workspace  (or: workspace current)
+    Shows the workspace this TUI session is working in.
+
+  workspace list  (or: workspace l)
+    Lists all workspaces; the current one is marked with '*'.
+
+  workspace create <name> [display name]
+    Creates an empty workspace. Names use lowercase letters, digits,
+    '-' and '_'.
+
+  workspace use <name>
+    Switches this session to <name>. The 'command', 'redeem', 'config',
+    'account' and 'credential' commands then read and write that
+    workspace's data. 'workspace use default' switches back.
+
+  workspace delete <name> [--yes]
+    Deletes the workspace together with its commands, redeems, config and
+    channel routes. Refused while the workspace still has credentials:
+    remove its accounts first. Asks for confirmation unless --yes is given.
+    The default workspace cannot be deleted.
+
+  workspace channels
+    Lists the chat channels routed to a workspace.
+
+  workspace channel add <platform> <channel>
+    Answers <channel> with the current workspace's commands, redeems and
+    config. All Twitch runtimes share one route ('twitch-irc', 'twitch'
+    and 'twitch-eventsub' name the same channel).
+
+  workspace channel remove <platform> <channel>
+    Sends <channel> back to the default workspace.
+
+Notes:
+  - Existing data lives in the 'default' workspace, which is also used when
+    no workspace is selected.
+  - Accounts added with 'account add' while a workspace is selected belong
+    to that workspace. A platform account can only belong to one workspace.
+  - Chat channels without a route use the default workspace's commands,
+    redeems and config. Replies go out as the routed workspace's own bot
+    account when it has one.
+  - Other gRPC clients select a workspace with the x-maowbot-workspace
+    metadata header (workspace name or UUID).
+
+Examples:
+  workspace create kitty "Kitty's channel"
+  workspace use kitty
+  workspace channel add twitch-irc kittyn
+  command list twitch-irc
+  workspace use default
+"#;
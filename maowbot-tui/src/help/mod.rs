@@ -26,6 +26,23 @@ pub mod help_system;
 pub mod help_osc;
 pub mod help_obs;
 pub mod help_pipeline;
+pub mod help_script;
+pub mod help_watch;
+pub mod help_alias;
+pub mod help_workspace;
+pub mod help_token;
+pub mod help_audit;
+pub mod help_chatlog;
+pub mod help_export;
+pub mod help_giveaway;
+pub mod help_vote;
+pub mod help_protect;
+pub mod help_automod;
+pub mod help_responder;
+pub mod help_emotes;
+pub mod help_language;
+pub mod help_alerting;
+pub mod help_session;
 
 fn show_general_help() -> String {
     let text = r#"MaowBot TUI - Available Commands:
@@ -34,20 +51,34 @@ Core Commands:
   help [command]         Show general help or detailed help for a command
   status [config]        Show system status (add 'config' to include settings)
   list                   List all known plugins
+  alias                  Define command shortcuts and macros
   quit                   Shut down the TUI
 
 User Management:
   user                   Comprehensive user management (add, edit, search, roles, etc.)
   credential             Direct credential management (list, refresh, health)
+  token                  API tokens and roles for gRPC clients, access audit log
+  audit                  Who changed what: config, commands, credentials, plugins
+  chatlog                Search stored chat history, last seen, retention per channel
+  export                 Save chat, events or command uses to CSV/JSONL for analysis
 
 Platform Management:
   platform               Manage platform configurations (add, remove, list)
+  workspace              Switch between broadcasters managed by this server
   account                Manage platform accounts and credentials
   connection             Platform runtime control (start, stop, chat, autostart)
 
 Content Management:
   command                Manage chat commands (cooldowns, responses, enable/disable)
   redeem                 Manage channel point redeems
+  giveaway               Run giveaways (keyword or points entry, draws, re-rolls)
+  vote                   Crowd control: chat votes which OSC effect happens next
+  protect                Raid defense: Shield Mode, chat lockdowns, incident log
+  automod                Link/phrase/regex moderation rules with a test evaluator
+  responder              Regex/keyword chat responders with chance and cooldowns
+  emotes                 Emote usage stats and 7TV/BTTV/FFZ emote sets
+  session                Stream sessions with per-stream stats, markers, chatters
+  language               Bot response languages and per-channel languages
   config                 Bot configuration (list, set, delete, export, import)
   pipeline               Event pipeline management (filters, actions, history)
 
@@ -62,11 +93,18 @@ System & Development:
   plugin                 Plugin management (enable, disable, remove)
   ai                     AI provider configuration and chat
   diagnostics (diag)     System health monitoring and troubleshooting
+  alerting               Ops alerts to Discord DMs, chat or the desktop
   system                 Server and overlay process management
   test_harness           Testing framework for TUI functionality
   simulate               Trigger test events without going live
+  watch                  Live dashboards (status, osc, connections) and events
+
+Listing commands accept --limit N and --offset N. Output taller than the
+terminal opens in a pager: Enter for the next page, b to go back, /text to
+search (n/N to repeat), q to quit. Start with --no-pager to disable it.
 
 Type 'help <command>' for detailed information about any command.
+Type 'help script' for running commands non-interactively.
 "#;
     text.to_owned()
 }
@@ -78,6 +116,7 @@ pub fn show_command_help(command: &str) -> String {
         // Core Commands
         "status" => "Status Command:\n  Usage: status [config]\n    Shows system uptime and connected plugins.\n    Add 'config' to include bot_config entries.".to_owned(),
         "list" => "List Command:\n  Usage: list\n    Shows all known plugins (enabled or disabled).".to_owned(),
+        "alias" => help_alias::ALIAS_HELP_TEXT.to_owned(),
         "quit" => "Quit Command:\n  Usage: quit\n    Shuts down the TUI and the entire bot process.".to_owned(),
 
         // User Management
@@ -93,6 +132,20 @@ pub fn show_command_help(command: &str) -> String {
         "command" => help_command::COMMAND_HELP_TEXT.to_owned(),
         "redeem" => help_redeem::REDEEM_HELP_TEXT.to_owned(),
         "config" => help_config::CONFIG_HELP_TEXT.to_owned(),
+        "workspace" => help_workspace::WORKSPACE_HELP_TEXT.to_owned(),
+        "token" => help_token::TOKEN_HELP_TEXT.to_owned(),
+        "audit" => help_audit::AUDIT_HELP_TEXT.to_owned(),
+        "chatlog" => help_chatlog::CHATLOG_HELP_TEXT.to_owned(),
+        "export" => help_export::EXPORT_HELP_TEXT.to_owned(),
+        "giveaway" => help_giveaway::GIVEAWAY_HELP_TEXT.to_owned(),
+        "vote" => help_vote::VOTE_HELP_TEXT.to_owned(),
+        "protect" => help_protect::PROTECT_HELP_TEXT.to_owned(),
+        "automod" => help_automod::AUTOMOD_HELP_TEXT.to_owned(),
+        "responder" => help_responder::RESPONDER_HELP_TEXT.to_owned(),
+        "emotes" => help_emotes::EMOTES_HELP_TEXT.to_owned(),
+        "session" => help_session::SESSION_HELP_TEXT.to_owned(),
+        "language" => help_language::LANGUAGE_HELP_TEXT.to_owned(),
+        "alerting" => help_alerting::ALERTING_HELP_TEXT.to_owned(),
         "pipeline" => help_pipeline::help_pipeline(),
 
         // Platform-Specific
@@ -110,6 +163,8 @@ pub fn show_command_help(command: &str) -> String {
         "system" => help_system::system_help().to_owned(),
         "test_harness" => help_test_harness::help_test_harness(),
         "simulate" => help_simulate::help_simulate(),
+        "watch" => help_watch::WATCH_HELP_TEXT.to_owned(),
+        "script" | "exec" => help_script::SCRIPT_HELP_TEXT.to_owned(),
 
         // Legacy redirects
         "member" => "The 'member' command has been merged into 'user'.\nUse 'help user' for details.".to_owned(),
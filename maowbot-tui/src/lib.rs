@@ -7,6 +7,9 @@ pub mod help;
 pub mod test_harness;
 pub mod completion;
 pub mod unified_completer;
+pub mod script;
+pub mod aliases;
+pub mod pager;
 
 pub use tui_module::TuiModule;
 pub use tui_module_simple::SimpleTuiModule;
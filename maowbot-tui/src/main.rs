@@ -1,10 +1,18 @@
 // Standalone TUI client using gRPC
 use maowbot_common_ui::{GrpcClient, ProcessManager};
-use maowbot_tui::{commands::dispatch_grpc, SimpleTuiModule, completion::TuiCompleter};
+use maowbot_tui::{commands::dispatch_grpc, SimpleTuiModule, unified_completer::UnifiedCompleter};
+use maowbot_tui::script::{self, ScriptOptions};
+use maowbot_tui::pager;
+use maowbot_common_ui::commands::health::HealthCommands;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+
+/// How long `--wait-ready` without a value, and autostart, wait for the server.
+const DEFAULT_WAIT_READY_SECS: u64 = 120;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -20,6 +28,43 @@ struct Args {
     /// Server URL to connect to
     #[arg(long, default_value = "https://127.0.0.1:9999")]
     server_url: String,
+    
+    /// Run commands from a file non-interactively, then exit
+    #[arg(long, value_name = "FILE")]
+    script: Option<PathBuf>,
+    
+    /// Print one JSON object per command (script/exec mode only)
+    #[arg(long, default_value_t = false)]
+    json: bool,
+    
+    /// Continue after a failed command (script/exec mode only)
+    #[arg(long, default_value_t = false)]
+    keep_going: bool,
+    
+    /// Print long output directly instead of through the built-in pager
+    #[arg(long, default_value_t = false)]
+    no_pager: bool,
+    
+    /// Workspace (broadcaster) to work in; defaults to the "default" workspace
+    #[arg(long, value_name = "NAME")]
+    workspace: Option<String>,
+    
+    /// Wait up to SECS for the server to finish starting (always done when autostarting it)
+    #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "120")]
+    wait_ready: Option<u64>,
+    
+    #[command(subcommand)]
+    command: Option<Mode>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Mode {
+    /// Run one or more `;`-separated commands, then exit
+    Exec {
+        /// Commands to run, e.g. "platform list; pipeline list all"
+        #[arg(required = true, num_args = 1..)]
+        commands: Vec<String>,
+    },
 }
 
 #[tokio::main]
@@ -27,46 +72,108 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
     let args = Args::parse();
     
+    // In script/exec mode stdout carries only command output, so everything else goes to stderr
+    let script_source = match (&args.script, &args.command) {
+        (Some(path), _) => Some(std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read script {}: {}", path.display(), e))?),
+        (None, Some(Mode::Exec { commands })) => Some(commands.join(" ")),
+        (None, None) => None,
+    };
+    let batch = script_source.is_some();
+    
     // Initialize logging
-    tracing_subscriber::fmt::init();
+    if batch {
+        tracing_subscriber::fmt().with_writer(std::io::stderr).init();
+    } else {
+        tracing_subscriber::fmt::init();
+    }
+    
+    let status = |msg: &str| if batch { eprintln!("{}", msg) } else { println!("{}", msg) };
 
-    println!("MaowBot TUI (gRPC mode)");
+    status("MaowBot TUI (gRPC mode)");
     
     // Create process manager
     let process_manager = Arc::new(ProcessManager::new());
     
     // Determine server URL
     let server_url = if args.no_autostart {
-        println!("Connecting to existing server at {}...", args.server_url);
+        status(&format!("Connecting to existing server at {}...", args.server_url));
         args.server_url.clone()
     } else {
         // Ensure server is running
-        println!("Checking server status...");
+        status("Checking server status...");
         process_manager.ensure_server_running().await?
     };
     
-    println!("Connecting to gRPC server at {}...", server_url);
+    status(&format!("Connecting to gRPC server at {}...", server_url));
 
-    // Connect to gRPC server
-    let client = match GrpcClient::connect(&server_url).await {
-        Ok(c) => {
-            println!("✅ Connected to gRPC server!");
-            c
+    // A server we just started may still be coming up, so wait for it by default
+    let wait_ready = args.wait_ready
+        .or(if args.no_autostart { None } else { Some(DEFAULT_WAIT_READY_SECS) })
+        .map(Duration::from_secs);
+    let deadline = wait_ready.map(|timeout| Instant::now() + timeout);
+
+    // Connect to gRPC server, retrying until the deadline when waiting
+    let client = loop {
+        match GrpcClient::connect(&server_url).await {
+            Ok(c) => {
+                status("✅ Connected to gRPC server!");
+                break c;
+            }
+            Err(e) if deadline.is_some_and(|d| Instant::now() < d) => {
+                tracing::debug!("Server not reachable yet: {}", e);
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+            Err(e) => {
+                status(&format!("❌ Failed to connect to gRPC server: {}", e));
+                return Err(e);
+            }
         }
-        Err(e) => {
-            println!("❌ Failed to connect to gRPC server: {}", e);
+    };
+
+    if let Some(deadline) = deadline {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let waited = HealthCommands::wait_ready(&client, remaining, |waiting| {
+            status(&format!("Server is starting: {}...", waiting))
+        }).await;
+        if let Err(e) = waited {
+            status(&format!("❌ {}", e));
             return Err(e.into());
         }
-    };
+    }
+
+    if let Some(name) = &args.workspace {
+        match maowbot_common_ui::commands::workspace::WorkspaceCommands::use_workspace(&client, name).await {
+            Ok(ws) => status(&format!("Using workspace '{}'", ws.name)),
+            Err(e) => return Err(format!("Cannot use workspace '{}': {}", name, e).into()),
+        }
+    }
 
     // Create a minimal TUI module for the gRPC client
     let tui_module = Arc::new(SimpleTuiModule::new());
+    
+    if let Some(source) = script_source {
+        let commands = script::split_commands(&source);
+        let options = ScriptOptions { json: args.json, keep_going: args.keep_going };
+        let summary = script::run_script(&commands, &client, &tui_module, &process_manager, &options).await;
+        
+        if args.stop_server_on_exit {
+            if let Err(e) = process_manager.stop(maowbot_common_ui::ProcessType::Server).await {
+                eprintln!("Warning: Failed to stop server: {}", e);
+            }
+        }
+        
+        if summary.failed > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
     println!("\nType 'help' for available commands.\n");
 
-    // Initialize readline with tab completion
-    let mut rl = Editor::<TuiCompleter, _>::new()?;
-    rl.set_helper(Some(TuiCompleter::new()));
+    // Initialize readline with tab completion backed by live server data
+    let mut rl = Editor::<UnifiedCompleter, _>::new()?;
+    rl.set_helper(Some(UnifiedCompleter::new(Arc::new(client.clone()))));
     
     // Load history if it exists
     let history_path = dirs::home_dir()
@@ -81,7 +188,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Main input loop
     loop {
-        let prompt = tui_module.prompt_string();
+        let prompt = tui_module.prompt_string(client.workspace().as_deref());
         
         let line = match rl.readline(&prompt) {
             Ok(line) => {
@@ -128,8 +235,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Otherwise, interpret line as a command
         let (quit_requested, output) = dispatch_grpc(&line, &client, &tui_module, &process_manager).await;
         
+        if let Some(helper) = rl.helper() {
+            helper.invalidate_after(&line);
+        }
+        
         if let Some(msg) = output {
-            println!("{}", msg);
+            if args.no_pager {
+                println!("{}", msg);
+            } else {
+                pager::print_output(&msg);
+            }
         }
         
         if quit_requested {
@@ -0,0 +1,226 @@
+// Interactive pager for command output taller than the terminal
+//
+// Reads one line of input per prompt (the terminal stays in cooked mode, so
+// keys are confirmed with Enter):
+//   Enter / space   next page          b        previous page
+//   g / G           first / last page  <n>      jump to line n
+//   /pattern        search forward     ?pattern search backward
+//   n / N           repeat search      q        quit
+use colored::Colorize;
+use std::io::{stdin, stdout, IsTerminal, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PagerStep {
+    Continue,
+    Quit,
+    Message(String),
+}
+
+/// Navigation state, kept separate from terminal I/O so it can be tested.
+#[derive(Debug)]
+pub struct PagerView {
+    lines: Vec<String>,
+    pub top: usize,
+    page_height: usize,
+    search: Option<(String, bool)>,
+}
+
+impl PagerView {
+    pub fn new(text: &str, page_height: usize) -> Self {
+        Self {
+            lines: text.lines().map(str::to_string).collect(),
+            top: 0,
+            page_height: page_height.max(1),
+            search: None,
+        }
+    }
+
+    fn last_top(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height)
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.top >= self.last_top()
+    }
+
+    /// The visible slice, with the current search term highlighted.
+    pub fn render(&self) -> String {
+        let end = (self.top + self.page_height).min(self.lines.len());
+        let mut out = String::new();
+        for line in &self.lines[self.top..end] {
+            match &self.search {
+                Some((pattern, _)) => out.push_str(&highlight(line, pattern)),
+                None => out.push_str(line),
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn prompt(&self) -> String {
+        let end = (self.top + self.page_height).min(self.lines.len());
+        if self.at_end() {
+            format!("(END - lines {}-{} of {}; q to quit, b back, /search) ", self.top + 1, end, self.lines.len())
+        } else {
+            format!("-- lines {}-{} of {} -- Enter next, b back, /search, q quit: ", self.top + 1, end, self.lines.len())
+        }
+    }
+
+    pub fn handle(&mut self, input: &str) -> PagerStep {
+        match input.trim() {
+            "" | "f" => {
+                if self.at_end() {
+                    return PagerStep::Quit;
+                }
+                self.top = (self.top + self.page_height).min(self.last_top());
+            }
+            "q" | "Q" => return PagerStep::Quit,
+            "b" => self.top = self.top.saturating_sub(self.page_height),
+            "g" => self.top = 0,
+            "G" => self.top = self.last_top(),
+            "n" => return self.repeat_search(false),
+            "N" => return self.repeat_search(true),
+            other => {
+                if let Some(pattern) = other.strip_prefix('/') {
+                    return self.start_search(pattern, false);
+                }
+                if let Some(pattern) = other.strip_prefix('?') {
+                    return self.start_search(pattern, true);
+                }
+                match other.parse::<usize>() {
+                    Ok(line) => self.top = line.saturating_sub(1).min(self.last_top()),
+                    Err(_) => return PagerStep::Message(format!("Unknown pager command '{}'", other)),
+                }
+            }
+        }
+        PagerStep::Continue
+    }
+
+    fn start_search(&mut self, pattern: &str, backward: bool) -> PagerStep {
+        if pattern.is_empty() {
+            return self.repeat_search(false);
+        }
+        self.search = Some((pattern.to_string(), backward));
+        self.find(pattern, backward)
+    }
+
+    fn repeat_search(&mut self, reverse: bool) -> PagerStep {
+        match self.search.clone() {
+            Some((pattern, backward)) => self.find(&pattern, backward != reverse),
+            None => PagerStep::Message("No previous search".to_string()),
+        }
+    }
+
+    /// Move so the next matching line (after/before the current top) is at the top.
+    fn find(&mut self, pattern: &str, backward: bool) -> PagerStep {
+        let needle = pattern.to_lowercase();
+        let matches = |idx: &usize| self.lines[*idx].to_lowercase().contains(&needle);
+        let found = if backward {
+            (0..self.top).rev().find(matches)
+        } else {
+            (self.top + 1..self.lines.len()).find(matches)
+        };
+        match found {
+            Some(idx) => {
+                self.top = idx;
+                PagerStep::Continue
+            }
+            None => PagerStep::Message(format!("Pattern not found: {}", pattern)),
+        }
+    }
+}
+
+fn highlight(line: &str, pattern: &str) -> String {
+    let lower = line.to_lowercase();
+    let needle = pattern.to_lowercase();
+    // Lowercasing can change byte lengths outside ASCII; only highlight when offsets line up
+    if needle.is_empty() || lower.len() != line.len() {
+        return line.to_string();
+    }
+    let mut out = String::new();
+    let mut last = 0;
+    for (start, _) in lower.match_indices(&needle) {
+        out.push_str(&line[last..start]);
+        out.push_str(&line[start..start + needle.len()].reversed().to_string());
+        last = start + needle.len();
+    }
+    out.push_str(&line[last..]);
+    out
+}
+
+/// Print `output`, paging it when stdout is a terminal and it does not fit on one screen.
+pub fn print_output(output: &str) {
+    let height = match terminal_size::terminal_size() {
+        Some((_, terminal_size::Height(h))) if stdout().is_terminal() && stdin().is_terminal() => h as usize,
+        _ => {
+            println!("{}", output);
+            return;
+        }
+    };
+    // Leave a row for the prompt
+    let page_height = height.saturating_sub(1).max(1);
+    if output.lines().count() <= page_height {
+        println!("{}", output);
+        return;
+    }
+
+    let mut view = PagerView::new(output, page_height);
+    let mut message: Option<String> = None;
+    loop {
+        print!("\x1B[2J\x1B[H{}", view.render());
+        if let Some(msg) = message.take() {
+            print!("{} ", msg.yellow());
+        }
+        print!("{}", view.prompt());
+        let _ = stdout().flush();
+
+        let mut input = String::new();
+        match stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        match view.handle(&input) {
+            PagerStep::Continue => {}
+            PagerStep::Quit => break,
+            PagerStep::Message(msg) => message = Some(msg),
+        }
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(n: usize) -> String {
+        (1..=n).map(|i| format!("line {}", i)).collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut view = PagerView::new(&numbered(25), 10);
+        assert_eq!(view.handle(""), PagerStep::Continue);
+        assert_eq!(view.top, 10);
+        assert_eq!(view.handle(""), PagerStep::Continue);
+        assert_eq!(view.top, 15); // clamped so the last page is full
+        assert!(view.at_end());
+        assert_eq!(view.handle(""), PagerStep::Quit);
+        view.handle("b");
+        assert_eq!(view.top, 5);
+        view.handle("3");
+        assert_eq!(view.top, 2);
+    }
+
+    #[test]
+    fn test_search() {
+        let mut view = PagerView::new(&numbered(30), 5);
+        assert_eq!(view.handle("/LINE 2"), PagerStep::Continue);
+        assert_eq!(view.top, 1); // "line 2"
+        view.handle("n");
+        assert_eq!(view.top, 19); // "line 20"
+        view.handle("N");
+        assert_eq!(view.top, 1);
+        assert!(matches!(view.handle("/missing"), PagerStep::Message(_)));
+        assert!(view.render().contains("line 2"));
+    }
+}
@@ -0,0 +1,178 @@
+// Non-interactive execution of TUI commands (`--script <file>` / `exec "<cmd>; <cmd>"`)
+use std::sync::Arc;
+use serde::Serialize;
+use maowbot_common_ui::{GrpcClient, ProcessManager};
+use crate::commands::dispatch_grpc;
+use crate::tui_module_simple::SimpleTuiModule;
+
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOptions {
+    /// Emit one JSON object per command instead of plain text
+    pub json: bool,
+    /// Keep running after a command fails instead of stopping at the first error
+    pub keep_going: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptCommandResult {
+    pub line: usize,
+    pub command: String,
+    pub success: bool,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptSummary {
+    pub executed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// Split script text into commands.
+///
+/// Commands are separated by newlines or `;` outside of quotes. Blank lines and
+/// lines starting with `#` are ignored. Each command is paired with the 1-based
+/// source line it came from.
+pub fn split_commands(source: &str) -> Vec<(usize, String)> {
+    let mut commands = Vec::new();
+
+    for (idx, raw_line) in source.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut current = String::new();
+        let mut quote: Option<char> = None;
+        for ch in line.chars() {
+            match (ch, quote) {
+                ('"' | '\'', None) => {
+                    quote = Some(ch);
+                    current.push(ch);
+                }
+                (c, Some(q)) if c == q => {
+                    quote = None;
+                    current.push(ch);
+                }
+                (';', None) => {
+                    push_command(&mut commands, idx + 1, &current);
+                    current.clear();
+                }
+                _ => current.push(ch),
+            }
+        }
+        push_command(&mut commands, idx + 1, &current);
+    }
+
+    commands
+}
+
+fn push_command(commands: &mut Vec<(usize, String)>, line: usize, text: &str) {
+    let text = text.trim();
+    if !text.is_empty() {
+        commands.push((line, text.to_string()));
+    }
+}
+
+/// Adapters report failures as text, so classify output by its leading words.
+pub fn output_indicates_failure(output: &str) -> bool {
+    let first_line = output.trim_start().lines().next().unwrap_or("");
+    ["Error", "Failed", "Unknown command", "Usage:", "System command error", "❌"]
+        .iter()
+        .any(|prefix| first_line.starts_with(prefix))
+}
+
+/// Run each command through the normal dispatcher, printing results as they complete.
+///
+/// Returns the summary; callers decide the process exit code from `failed`.
+pub async fn run_script(
+    commands: &[(usize, String)],
+    client: &GrpcClient,
+    tui_module: &Arc<SimpleTuiModule>,
+    process_manager: &Arc<ProcessManager>,
+    options: &ScriptOptions,
+) -> ScriptSummary {
+    let mut executed = 0;
+    let mut failed = 0;
+
+    for (line, command) in commands {
+        let (quit_requested, output) = dispatch_grpc(command, client, tui_module, process_manager).await;
+        let output = output.unwrap_or_default();
+        let success = !output_indicates_failure(&output);
+
+        executed += 1;
+        if !success {
+            failed += 1;
+        }
+
+        let result = ScriptCommandResult {
+            line: *line,
+            command: command.clone(),
+            success,
+            output,
+        };
+        print_result(&result, options);
+
+        if quit_requested || (!success && !options.keep_going) {
+            break;
+        }
+    }
+
+    let summary = ScriptSummary {
+        executed,
+        failed,
+        skipped: commands.len() - executed,
+    };
+
+    if options.json {
+        println!("{}", serde_json::json!({ "summary": summary }));
+    } else if summary.failed > 0 || summary.skipped > 0 {
+        eprintln!(
+            "{} command(s) executed, {} failed, {} skipped",
+            summary.executed, summary.failed, summary.skipped
+        );
+    }
+
+    summary
+}
+
+fn print_result(result: &ScriptCommandResult, options: &ScriptOptions) {
+    if options.json {
+        // One object per line so CI jobs can stream-parse the output
+        match serde_json::to_string(result) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error serializing result: {}", e),
+        }
+    } else {
+        println!("> {}", result.command);
+        if !result.output.is_empty() {
+            println!("{}", result.output);
+        }
+        if !result.success {
+            eprintln!("Command on line {} failed", result.line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_commands() {
+        let script = "# setup\nplatform list; user list\n\npipeline test x chat_message {\"text\": \"a;b\"}\n";
+        let commands = split_commands(script);
+        assert_eq!(commands, vec![
+            (2, "platform list".to_string()),
+            (2, "user list".to_string()),
+            (4, "pipeline test x chat_message {\"text\": \"a;b\"}".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_output_indicates_failure() {
+        assert!(output_indicates_failure("Error listing pipelines: boom"));
+        assert!(output_indicates_failure("Unknown command 'foo'. Type 'help' for usage."));
+        assert!(!output_indicates_failure("Pipeline welcome enabled."));
+    }
+}
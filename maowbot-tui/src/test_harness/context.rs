@@ -21,6 +21,8 @@ pub struct TestState {
     pub chat_messages: Vec<ChatMessage>,
     pub executed_commands: Vec<ExecutedCommand>,
     pub executed_redeems: Vec<ExecutedRedeem>,
+    pub osc_parameters: HashMap<String, serde_json::Value>,
+    pub simulated_events: Vec<SimulatedEvent>,
 }
 
 #[derive(Clone, Debug)]
@@ -49,6 +51,19 @@ pub struct ExecutedRedeem {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Clone, Debug)]
+pub struct SimulatedEvent {
+    pub event_type: String,
+    pub data: serde_json::Value,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestContext {
     pub fn new() -> Self {
         Self {
@@ -94,9 +109,9 @@ impl TestContext {
         });
 
         // Check if this is a command
-        if message.starts_with('!') {
-            let parts: Vec<&str> = message[1..].split_whitespace().collect();
-            if let Some(cmd_name) = parts.get(0) {
+        if let Some(command_line) = message.strip_prefix('!') {
+            let parts: Vec<&str> = command_line.split_whitespace().collect();
+            if let Some(cmd_name) = parts.first() {
                 if state.commands.contains_key(*cmd_name) {
                     state.executed_commands.push(ExecutedCommand {
                         command: cmd_name.to_string(),
@@ -119,6 +134,36 @@ impl TestContext {
             platform: self.current_platform.clone(),
             timestamp: chrono::Utc::now(),
         });
+
+        // OSC toggle redeems flip a bool parameter named after the reward
+        let is_osc_toggle = state.redeems.get(redeem_name)
+            .and_then(|r| r.command_name.as_deref())
+            == Some("osc_toggle");
+        if is_osc_toggle {
+            let current = state.osc_parameters.get(redeem_name)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            state.osc_parameters.insert(redeem_name.to_string(), serde_json::Value::Bool(!current));
+        }
+    }
+
+    pub async fn simulate_event(&self, event_type: &str, data: serde_json::Value) {
+        let mut state = self.state.lock().await;
+        state.simulated_events.push(SimulatedEvent {
+            event_type: event_type.to_string(),
+            data,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    pub async fn set_osc_parameter(&self, name: &str, value: serde_json::Value) {
+        let mut state = self.state.lock().await;
+        state.osc_parameters.insert(name.to_string(), value);
+    }
+
+    pub async fn get_osc_parameter(&self, name: &str) -> Option<serde_json::Value> {
+        let state = self.state.lock().await;
+        state.osc_parameters.get(name).cloned()
     }
 
     pub async fn get_chat_messages(&self) -> Vec<ChatMessage> {
@@ -141,6 +186,7 @@ impl TestContext {
         state.chat_messages.clear();
         state.executed_commands.clear();
         state.executed_redeems.clear();
+        state.simulated_events.clear();
     }
 
     pub async fn assert_command_executed(&self, command: &str, user: &str) -> bool {
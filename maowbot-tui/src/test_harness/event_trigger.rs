@@ -1,8 +1,5 @@
 use std::sync::Arc;
-use chrono::Utc;
-use uuid::Uuid;
-use serde_json::json;
-use maowbot_common::traits::api::{BotApi, TwitchApi};
+use maowbot_common::traits::api::BotApi;
 
 pub struct EventTrigger {
     bot_api: Arc<dyn BotApi>,
@@ -92,7 +89,7 @@ impl EventTrigger {
         ];
 
         for (cmd, args) in commands {
-            self.trigger_command(account_name, channel, cmd, &args.iter().map(|s| *s).collect::<Vec<_>>()).await?;
+            self.trigger_command(account_name, channel, cmd, &args.to_vec()).await?;
             tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
         }
         Ok(())
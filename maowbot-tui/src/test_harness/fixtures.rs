@@ -63,6 +63,12 @@ pub fn ping_command() -> Command {
         is_active: true,
         cooldown_seconds: 5,
         cooldown_warnonce: false,
+        user_cooldown_seconds: 0,
+        role_cooldowns: Default::default(),
+        cooldown_bypass_mods: false,
+        reply_privately: false,
+        reply_to_message: false,
+        response: None,
         respond_with_credential: None,
         stream_online_only: false,
         stream_offline_only: false,
@@ -81,6 +87,12 @@ pub fn followage_command() -> Command {
         is_active: true,
         cooldown_seconds: 10,
         cooldown_warnonce: false,
+        user_cooldown_seconds: 0,
+        role_cooldowns: Default::default(),
+        cooldown_bypass_mods: false,
+        reply_privately: false,
+        reply_to_message: false,
+        response: None,
         respond_with_credential: None,
         stream_online_only: false,
         stream_offline_only: false,
@@ -99,6 +111,12 @@ pub fn vanish_command() -> Command {
         is_active: true,
         cooldown_seconds: 30,
         cooldown_warnonce: false,
+        user_cooldown_seconds: 0,
+        role_cooldowns: Default::default(),
+        cooldown_bypass_mods: false,
+        reply_privately: false,
+        reply_to_message: false,
+        response: None,
         respond_with_credential: None,
         stream_online_only: false,
         stream_offline_only: false,
@@ -117,6 +135,12 @@ pub fn so_command() -> Command {
         is_active: true,
         cooldown_seconds: 60,
         cooldown_warnonce: false,
+        user_cooldown_seconds: 0,
+        role_cooldowns: Default::default(),
+        cooldown_bypass_mods: false,
+        reply_privately: false,
+        reply_to_message: false,
+        response: None,
         respond_with_credential: None,
         stream_online_only: false,
         stream_offline_only: false,
@@ -143,6 +167,7 @@ pub fn cute_redeem() -> Redeem {
         active_credential_id: None,
         is_input_required: false,
         redeem_prompt_text: None,
+        requires_approval: false,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -164,6 +189,7 @@ pub fn tts_redeem() -> Redeem {
         active_credential_id: None,
         is_input_required: true,
         redeem_prompt_text: Some("Enter your TTS message".to_string()),
+        requires_approval: false,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -185,6 +211,7 @@ pub fn osc_toggle_redeem() -> Redeem {
         active_credential_id: None,
         is_input_required: false,
         redeem_prompt_text: None,
+        requires_approval: false,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
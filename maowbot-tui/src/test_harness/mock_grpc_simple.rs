@@ -1,14 +1,26 @@
+// Mocked calls fail with tonic's `Status`, which is large
+#![allow(clippy::result_large_err)]
+
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tonic::{Request, Response, Status};
 use prost::Message;
 
+/// Method name and encoded request of one call
+pub type CallRecord = (String, Vec<u8>);
+
 pub type ResponseCallback = Arc<dyn Fn(&str, &[u8]) -> Result<Vec<u8>, Status> + Send + Sync>;
 
 #[derive(Clone)]
 pub struct SimpleMockGrpcClient {
     responses: Arc<Mutex<HashMap<String, ResponseCallback>>>,
-    call_log: Arc<Mutex<Vec<(String, Vec<u8>)>>>,
+    call_log: Arc<Mutex<Vec<CallRecord>>>,
+}
+
+impl Default for SimpleMockGrpcClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SimpleMockGrpcClient {
@@ -36,7 +48,7 @@ impl SimpleMockGrpcClient {
         self.with_response(method, move |_, _| Err(status.clone()))
     }
 
-    pub fn get_calls(&self) -> Vec<(String, Vec<u8>)> {
+    pub fn get_calls(&self) -> Vec<CallRecord> {
         self.call_log.lock().unwrap().clone()
     }
 
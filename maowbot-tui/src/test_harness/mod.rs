@@ -3,10 +3,12 @@ pub mod fixtures;
 pub mod mock_grpc;
 pub mod mock_grpc_simple;
 pub mod runner;
+pub mod scenario;
 pub mod twitch_simulator;
 pub mod event_trigger;
 
 pub use context::TestContext;
 pub use fixtures::*;
 pub use mock_grpc::MockGrpcClient;
-pub use runner::{TestRunner, TestResult, assert, success};
\ No newline at end of file
+pub use runner::{TestRunner, TestResult, assert, success};
+pub use scenario::{Scenario, ScenarioReport};
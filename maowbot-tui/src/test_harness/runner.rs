@@ -34,6 +34,12 @@ pub struct TestRunner {
     tests: Vec<TestCase>,
 }
 
+impl Default for TestRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestRunner {
     pub fn new() -> Self {
         Self { tests: Vec::new() }
@@ -174,6 +180,12 @@ pub struct AssertionBuilder {
     assertions: Vec<Assertion>,
 }
 
+impl Default for AssertionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AssertionBuilder {
     pub fn new() -> Self {
         Self {
@@ -249,6 +261,23 @@ impl AssertionBuilder {
         self
     }
 
+    /// Record an assertion whose outcome was decided by the caller.
+    pub fn record(
+        mut self,
+        description: impl Into<String>,
+        passed: bool,
+        expected: Option<String>,
+        actual: Option<String>,
+    ) -> Self {
+        self.assertions.push(Assertion {
+            passed,
+            description: description.into(),
+            expected,
+            actual,
+        });
+        self
+    }
+
     pub fn build(self) -> TestResult {
         let passed = self.assertions.iter().all(|a| a.passed);
         let message = if passed {
@@ -0,0 +1,549 @@
+// Scenario files: a sequence of simulated events plus the outcomes they should produce
+//
+// A scenario runs either in memory (against a fresh TestContext, using the
+// harness fixtures) or live (each step is a TUI command sent to the connected
+// server). Example, in TOML:
+//
+//     name = "Cute redeem"
+//     [fixtures]
+//     redeems = ["cute"]
+//
+//     [[steps]]
+//     action = "redeem"
+//     user = "viewer123"
+//     redeem = "Be Cute"
+//
+//     [[expect]]
+//     type = "redeem_executed"
+//     redeem = "Be Cute"
+use std::path::Path;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::Value;
+use crate::test_harness::context::TestContext;
+use crate::test_harness::fixtures;
+use crate::test_harness::runner::{assert, AssertionBuilder, TestResult};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// How long live expectations are polled before they fail
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub fixtures: ScenarioFixtures,
+    #[serde(default)]
+    pub steps: Vec<ScenarioStep>,
+    #[serde(default)]
+    pub expect: Vec<Expectation>,
+}
+
+/// Named harness fixtures loaded into the in-memory context before the steps run.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ScenarioFixtures {
+    #[serde(default)]
+    pub users: Vec<String>,
+    #[serde(default)]
+    pub commands: Vec<String>,
+    #[serde(default)]
+    pub redeems: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScenarioStep {
+    /// A viewer chat message (live: sent as a simulated Discord message)
+    Chat { user: String, message: String },
+    /// A channel points redemption by name
+    Redeem {
+        user: String,
+        redeem: String,
+        #[serde(default)]
+        input: Option<String>,
+    },
+    /// A synthetic platform event, as accepted by the `simulate` command
+    Simulate {
+        event: String,
+        #[serde(default)]
+        overrides: Option<Value>,
+    },
+    /// Set an OSC avatar parameter (in memory only)
+    Osc { parameter: String, value: Value },
+    /// Any TUI command line (live only)
+    Command { line: String },
+    Wait { ms: u64 },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Expectation {
+    ChatSent { contains: String },
+    CommandExecuted {
+        command: String,
+        #[serde(default)]
+        user: Option<String>,
+    },
+    RedeemExecuted {
+        redeem: String,
+        #[serde(default)]
+        user: Option<String>,
+    },
+    OscParam { name: String, value: Value },
+    /// Rows in a table: users, commands, redeems, pipelines or pipeline_executions
+    DbRows {
+        table: String,
+        #[serde(default)]
+        contains: Option<String>,
+        #[serde(default)]
+        min: Option<usize>,
+    },
+    /// Output of a TUI command (live only)
+    Output {
+        command: String,
+        #[serde(default)]
+        contains: Option<String>,
+        #[serde(default)]
+        not_contains: Option<String>,
+    },
+}
+
+fn default_timeout_ms() -> u64 {
+    5000
+}
+
+impl Scenario {
+    /// Parse a scenario from text. `format` is "toml" or "json".
+    pub fn parse(text: &str, format: &str) -> Result<Self, String> {
+        match format {
+            "toml" => toml::from_str(text).map_err(|e| format!("Invalid TOML scenario: {}", e)),
+            "json" => serde_json::from_str(text).map_err(|e| format!("Invalid JSON scenario: {}", e)),
+            other => Err(format!("Unsupported scenario format '{}'", other)),
+        }
+    }
+
+    /// Load a scenario from disk, choosing the format from the file extension.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::parse(&text, "toml"),
+            Some("json") => Self::parse(&text, "json"),
+            _ => Err("Scenario must be a .toml or .json file".to_string()),
+        }
+    }
+}
+
+/// Runs TUI command lines for live scenarios.
+#[async_trait(?Send)]
+pub trait CommandExecutor {
+    async fn execute(&self, line: &str) -> String;
+}
+
+pub struct ScenarioReport {
+    pub name: String,
+    pub live: bool,
+    pub step_errors: Vec<String>,
+    pub result: TestResult,
+    pub duration: Duration,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.step_errors.is_empty() && self.result.passed
+    }
+
+    pub fn format(&self) -> String {
+        let mut out = format!(
+            "Scenario: {} ({}, {:.2}s)\n",
+            self.name.bold(),
+            if self.live { "live" } else { "in-memory" },
+            self.duration.as_secs_f64()
+        );
+        for error in &self.step_errors {
+            out.push_str(&format!("  {} {}\n", "✗ step:".red(), error));
+        }
+        for assertion in &self.result.assertions {
+            if assertion.passed {
+                out.push_str(&format!("  {} {}\n", "✓".green(), assertion.description));
+            } else {
+                out.push_str(&format!("  {} {}\n", "✗".red(), assertion.description));
+                if let Some(expected) = &assertion.expected {
+                    out.push_str(&format!("      {}: {}\n", "Expected".yellow(), expected));
+                }
+                if let Some(actual) = &assertion.actual {
+                    out.push_str(&format!("      {}: {}\n", "Actual".yellow(), actual));
+                }
+            }
+        }
+        out.push_str(&if self.passed() {
+            format!("  {}\n", "✓ PASSED".green().bold())
+        } else {
+            format!("  {}\n", "✗ FAILED".red().bold())
+        });
+        out
+    }
+}
+
+/// Run a scenario against a fresh in-memory context.
+pub async fn run_in_memory(scenario: &Scenario) -> ScenarioReport {
+    let start = Instant::now();
+    let ctx = TestContext::new();
+    let mut step_errors = Vec::new();
+
+    if let Err(e) = load_fixtures(&ctx, &scenario.fixtures).await {
+        step_errors.push(e);
+    }
+
+    for (idx, step) in scenario.steps.iter().enumerate() {
+        if let Err(e) = run_step_in_memory(&ctx, step).await {
+            step_errors.push(format!("#{}: {}", idx + 1, e));
+        }
+    }
+
+    let mut builder = assert();
+    for expectation in &scenario.expect {
+        builder = check_in_memory(&ctx, expectation, builder).await;
+    }
+
+    ScenarioReport {
+        name: scenario.name.clone(),
+        live: false,
+        step_errors,
+        result: builder.build(),
+        duration: start.elapsed(),
+    }
+}
+
+/// Run a scenario against the connected server, sending each step as a TUI command.
+pub async fn run_live(scenario: &Scenario, executor: &dyn CommandExecutor) -> ScenarioReport {
+    let start = Instant::now();
+    let mut step_errors = Vec::new();
+
+    for (idx, step) in scenario.steps.iter().enumerate() {
+        if let ScenarioStep::Wait { ms } = step {
+            tokio::time::sleep(Duration::from_millis(*ms)).await;
+            continue;
+        }
+        let line = match live_command(step) {
+            Ok(line) => line,
+            Err(e) => {
+                step_errors.push(format!("#{}: {}", idx + 1, e));
+                continue;
+            }
+        };
+        let output = executor.execute(&line).await;
+        if crate::script::output_indicates_failure(&output) || output.trim_start().starts_with('✗') {
+            step_errors.push(format!("#{} '{}': {}", idx + 1, line, output.lines().next().unwrap_or("")));
+        }
+    }
+
+    let mut builder = assert();
+    for expectation in &scenario.expect {
+        builder = check_live(executor, expectation, scenario.timeout_ms, builder).await;
+    }
+
+    ScenarioReport {
+        name: scenario.name.clone(),
+        live: true,
+        step_errors,
+        result: builder.build(),
+        duration: start.elapsed(),
+    }
+}
+
+async fn load_fixtures(ctx: &TestContext, names: &ScenarioFixtures) -> Result<(), String> {
+    for name in &names.users {
+        let user = match name.as_str() {
+            "viewer" => fixtures::viewer_user(),
+            "moderator" => fixtures::moderator_user(),
+            "vip" => fixtures::vip_user(),
+            "subscriber" => fixtures::subscriber_user(),
+            "broadcaster" => fixtures::broadcaster_user(),
+            other => return Err(format!("Unknown user fixture '{}'", other)),
+        };
+        ctx.add_user(user).await;
+    }
+    for name in &names.commands {
+        let command = match name.as_str() {
+            "ping" => fixtures::ping_command(),
+            "followage" => fixtures::followage_command(),
+            "vanish" => fixtures::vanish_command(),
+            "so" => fixtures::so_command(),
+            other => return Err(format!("Unknown command fixture '{}'", other)),
+        };
+        ctx.add_command(command).await;
+    }
+    for name in &names.redeems {
+        let redeem = match name.as_str() {
+            "cute" => fixtures::cute_redeem(),
+            "tts" => fixtures::tts_redeem(),
+            "osc_toggle" => fixtures::osc_toggle_redeem(),
+            other => return Err(format!("Unknown redeem fixture '{}'", other)),
+        };
+        ctx.add_redeem(redeem).await;
+    }
+    Ok(())
+}
+
+async fn run_step_in_memory(ctx: &TestContext, step: &ScenarioStep) -> Result<(), String> {
+    match step {
+        ScenarioStep::Chat { user, message } => ctx.simulate_chat_message(user, message).await,
+        ScenarioStep::Redeem { user, redeem, input } => {
+            ctx.simulate_redeem(user, redeem, input.clone()).await
+        }
+        ScenarioStep::Simulate { event, overrides } => {
+            let data = overrides.clone().unwrap_or(Value::Null);
+            // A redemption event also runs the redeem, like the live redeem service would
+            if matches!(event.as_str(), "redeem" | "channel.channel_points_custom_reward_redemption.add") {
+                let title = data.pointer("/reward/title").and_then(Value::as_str)
+                    .ok_or("Simulated redemption needs overrides.reward.title in memory")?;
+                let user = data.get("user_login").and_then(Value::as_str).unwrap_or("test_viewer");
+                let input = data.get("user_input").and_then(Value::as_str)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string);
+                ctx.simulate_redeem(user, title, input).await;
+            }
+            ctx.simulate_event(event, data).await;
+        }
+        ScenarioStep::Osc { parameter, value } => ctx.set_osc_parameter(parameter, value.clone()).await,
+        ScenarioStep::Command { line } => {
+            return Err(format!("'command' steps need a live server (--live): {}", line));
+        }
+        ScenarioStep::Wait { ms } => tokio::time::sleep(Duration::from_millis(*ms)).await,
+    }
+    Ok(())
+}
+
+async fn check_in_memory(ctx: &TestContext, expectation: &Expectation, builder: AssertionBuilder) -> AssertionBuilder {
+    match expectation {
+        Expectation::ChatSent { contains } => builder.assert_true(
+            format!("Chat message containing '{}'", contains),
+            ctx.assert_message_sent(contains).await,
+        ),
+        Expectation::CommandExecuted { command, user } => {
+            let executed = ctx.get_executed_commands().await.iter()
+                .any(|c| &c.command == command && user.as_ref().is_none_or(|u| &c.user == u));
+            builder.assert_true(format!("Command '{}' executed", command), executed)
+        }
+        Expectation::RedeemExecuted { redeem, user } => {
+            let executed = ctx.get_executed_redeems().await.iter()
+                .any(|r| &r.redeem == redeem && user.as_ref().is_none_or(|u| &r.user == u));
+            builder.assert_true(format!("Redeem '{}' executed", redeem), executed)
+        }
+        Expectation::OscParam { name, value } => builder.assert_eq(
+            format!("OSC parameter '{}'", name),
+            Some(value.clone()),
+            ctx.get_osc_parameter(name).await,
+        ),
+        Expectation::DbRows { table, contains, min } => {
+            let rows = match memory_rows(ctx, table).await {
+                Ok(rows) => rows,
+                Err(e) => return builder.assert_true(e, false),
+            };
+            let matching = rows.iter()
+                .filter(|row| contains.as_ref().is_none_or(|c| row.contains(c.as_str())))
+                .count();
+            builder.assert_true(
+                describe_rows(table, contains, *min),
+                matching >= min.unwrap_or(1),
+            )
+        }
+        Expectation::Output { command, .. } => builder.assert_true(
+            format!("Output of '{}' (needs a live server: --live)", command),
+            false,
+        ),
+    }
+}
+
+async fn memory_rows(ctx: &TestContext, table: &str) -> Result<Vec<String>, String> {
+    let state = ctx.state.lock().await;
+    Ok(match table {
+        "users" => state.users.values().filter_map(|u| u.global_username.clone()).collect(),
+        "commands" => state.commands.keys().cloned().collect(),
+        "redeems" => state.redeems.keys().cloned().collect(),
+        "chat_messages" => state.chat_messages.iter().map(|m| format!("{}: {}", m.user, m.message)).collect(),
+        "redeem_usage" => state.executed_redeems.iter().map(|r| format!("{} {}", r.redeem, r.user)).collect(),
+        "command_usage" => state.executed_commands.iter().map(|c| format!("{} {}", c.command, c.user)).collect(),
+        other => return Err(format!("Unknown in-memory table '{}'", other)),
+    })
+}
+
+fn describe_rows(table: &str, contains: &Option<String>, min: Option<usize>) -> String {
+    match contains {
+        Some(c) => format!("At least {} row(s) in {} containing '{}'", min.unwrap_or(1), table, c),
+        None => format!("At least {} row(s) in {}", min.unwrap_or(1), table),
+    }
+}
+
+/// The TUI command a step turns into on a live server.
+fn live_command(step: &ScenarioStep) -> Result<String, String> {
+    match step {
+        ScenarioStep::Simulate { event, overrides } => Ok(match overrides {
+            Some(o) => format!("simulate {} {}", event, o),
+            None => format!("simulate {}", event),
+        }),
+        ScenarioStep::Command { line } => Ok(line.clone()),
+        ScenarioStep::Chat { user, message } => Ok(format!(
+            "simulate discord {}",
+            serde_json::json!({ "username": user, "text": message })
+        )),
+        ScenarioStep::Redeem { user, redeem, input } => Ok(format!(
+            "simulate redeem {}",
+            serde_json::json!({
+                "user_login": user,
+                "user_name": user,
+                "user_input": input.clone().unwrap_or_default(),
+                "reward": { "title": redeem },
+            })
+        )),
+        ScenarioStep::Osc { parameter, .. } => {
+            Err(format!("'osc' steps only run in memory (parameter '{}')", parameter))
+        }
+        ScenarioStep::Wait { .. } => Err("'wait' steps have no command".to_string()),
+    }
+}
+
+/// Listing command used to check `db_rows` on a live server.
+fn live_table_command(table: &str) -> Option<&'static str> {
+    match table {
+        "users" => Some("user list --limit 1000"),
+        "commands" => Some("command list"),
+        "redeems" => Some("redeem list"),
+        "pipelines" => Some("pipeline list all"),
+        "pipeline_executions" => Some("pipeline history --limit 100"),
+        _ => None,
+    }
+}
+
+async fn check_live(
+    executor: &dyn CommandExecutor,
+    expectation: &Expectation,
+    timeout_ms: u64,
+    builder: AssertionBuilder,
+) -> AssertionBuilder {
+    let (command, description, contains, not_contains, min) = match expectation {
+        Expectation::Output { command, contains, not_contains } => (
+            command.clone(),
+            format!("Output of '{}'", command),
+            contains.clone(),
+            not_contains.clone(),
+            None,
+        ),
+        Expectation::DbRows { table, contains, min } => match live_table_command(table) {
+            Some(command) => (command.to_string(), describe_rows(table, contains, *min), contains.clone(), None, *min),
+            None => return builder.assert_true(format!("Unknown live table '{}'", table), false),
+        },
+        // The server exposes no history for these, so they can only be checked in memory
+        Expectation::ChatSent { .. }
+        | Expectation::CommandExecuted { .. }
+        | Expectation::RedeemExecuted { .. }
+        | Expectation::OscParam { .. } => {
+            return builder.assert_true(
+                format!("{:?} is not observable on a live server; use an 'output' expectation", expectation),
+                false,
+            );
+        }
+    };
+
+    // Side effects may land asynchronously, so poll until the deadline
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    let mut output;
+    loop {
+        output = executor.execute(&command).await;
+        if live_output_matches(&output, contains.as_deref(), not_contains.as_deref(), min) {
+            return builder.assert_true(description, true);
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    let expected = match (&contains, &not_contains) {
+        (Some(c), Some(n)) => format!("contains '{}' and not '{}'", c, n),
+        (Some(c), None) => format!("contains '{}'", c),
+        (None, Some(n)) => format!("does not contain '{}'", n),
+        (None, None) => "a successful command".to_string(),
+    };
+    builder.record(description, false, Some(expected), Some(output.lines().take(5).collect::<Vec<_>>().join(" | ")))
+}
+
+fn live_output_matches(output: &str, contains: Option<&str>, not_contains: Option<&str>, min: Option<usize>) -> bool {
+    if crate::script::output_indicates_failure(output) {
+        return false;
+    }
+    if let Some(n) = not_contains {
+        if output.contains(n) {
+            return false;
+        }
+    }
+    match contains {
+        Some(c) => output.lines().filter(|line| line.contains(c)).count() >= min.unwrap_or(1),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+name = "Redeems and commands"
+
+[fixtures]
+commands = ["ping"]
+redeems = ["osc_toggle"]
+
+[[steps]]
+action = "chat"
+user = "viewer123"
+message = "!ping"
+
+[[steps]]
+action = "redeem"
+user = "viewer123"
+redeem = "Toggle Avatar Feature"
+
+[[expect]]
+type = "command_executed"
+command = "ping"
+user = "viewer123"
+
+[[expect]]
+type = "osc_param"
+name = "Toggle Avatar Feature"
+value = true
+
+[[expect]]
+type = "db_rows"
+table = "redeem_usage"
+contains = "viewer123"
+"#;
+
+    #[tokio::test]
+    async fn test_in_memory_scenario_passes() {
+        let scenario = Scenario::parse(SCENARIO, "toml").unwrap();
+        let report = run_in_memory(&scenario).await;
+        assert!(report.passed(), "{}", report.format());
+    }
+
+    #[tokio::test]
+    async fn test_failed_expectation_is_reported() {
+        let mut scenario = Scenario::parse(SCENARIO, "toml").unwrap();
+        scenario.expect.push(Expectation::ChatSent { contains: "never said".to_string() });
+        let report = run_in_memory(&scenario).await;
+        assert!(!report.passed());
+        assert_eq!(report.result.assertions.iter().filter(|a| !a.passed).count(), 1);
+    }
+
+    #[test]
+    fn test_live_output_matching() {
+        assert!(live_output_matches("a\nfoo 1\nfoo 2", Some("foo"), None, Some(2)));
+        assert!(!live_output_matches("foo", Some("foo"), Some("o"), None));
+        assert!(!live_output_matches("Error: nope foo", Some("foo"), None, None));
+    }
+}
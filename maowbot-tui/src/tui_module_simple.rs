@@ -3,13 +3,10 @@ use std::sync::{
     atomic::{AtomicBool, Ordering},
     Mutex
 };
-use std::io::{stdout, Write};
-
-use tokio::{io::BufReader};
-use tokio::io::AsyncBufReadExt;
 
 // Imported from parent module when needed
 use maowbot_common_ui::GrpcClient;
+use crate::aliases::AliasStore;
 
 /// Tracks state specific to Twitch-IRC in the TUI
 #[derive(Debug)]
@@ -22,6 +19,12 @@ pub struct TtvState {
     pub current_channel_index: usize,
 }
 
+impl Default for TtvState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TtvState {
     pub fn new() -> Self {
         Self {
@@ -49,6 +52,12 @@ pub struct OscState {
     pub is_in_chat_mode: bool,
 }
 
+impl Default for OscState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OscState {
     pub fn new() -> Self {
         Self {
@@ -63,6 +72,13 @@ pub struct SimpleTuiModule {
     pub chat_state: Arc<Mutex<ChatState>>,
     pub ttv_state: Arc<Mutex<TtvState>>,
     pub osc_state: Arc<Mutex<OscState>>,
+    pub aliases: Arc<Mutex<AliasStore>>,
+}
+
+impl Default for SimpleTuiModule {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SimpleTuiModule {
@@ -72,6 +88,7 @@ impl SimpleTuiModule {
             chat_state: Arc::new(Mutex::new(ChatState::default())),
             ttv_state: Arc::new(Mutex::new(TtvState::new())),
             osc_state: Arc::new(Mutex::new(OscState::new())),
+            aliases: Arc::new(Mutex::new(AliasStore::load_default())),
         }
     }
 
@@ -141,7 +158,8 @@ impl SimpleTuiModule {
         true
     }
 
-    pub fn prompt_string(&self) -> String {
+    /// `workspace` is the selected non-default workspace, shown in the normal prompt.
+    pub fn prompt_string(&self, workspace: Option<&str>) -> String {
         // TTV chat mode has precedence in this example.
         let st_ttv = self.ttv_state.lock().unwrap();
         if st_ttv.is_in_chat_mode {
@@ -160,7 +178,10 @@ impl SimpleTuiModule {
         }
 
         // default TUI prompt
-        "tui> ".to_string()
+        match workspace {
+            Some(ws) => format!("tui[{}]> ", ws),
+            None => "tui> ".to_string(),
+        }
     }
 
     pub async fn set_chat_state(
@@ -4,24 +4,30 @@ use rustyline::error::ReadlineError;
 use rustyline::highlight::{Highlighter, MatchingBracketHighlighter};
 use rustyline::hint::{Hinter, HistoryHinter};
 use rustyline::validate::{Validator, ValidationContext, ValidationResult};
-use rustyline::{Helper, Context};
+use rustyline::Context;
 use rustyline_derive::Helper;
 use std::borrow::Cow;
 use std::sync::Arc;
+use std::time::Duration;
 
 use maowbot_common_ui::completion::{
-    CompletionEngine, CompletionContext, CompletionScope, 
+    CompletionEngine, CompletionContext, CompletionScope, CompletionCache,
     CompletionEngineBuilder, CompletionConfig,
     providers::{
         TuiCommandCompletionProvider, CommandCompletionProvider,
-        EmoteCompletionProvider, UserCompletionProvider
+        EmoteCompletionProvider, UserCompletionProvider, PipelineCompletionProvider,
+        ArgumentCompletionProvider, ArgumentKind,
     }
 };
 use maowbot_common_ui::GrpcClient;
 
+/// How long a Tab press may wait on the server before giving up on live data
+const COMPLETION_TIMEOUT: Duration = Duration::from_millis(750);
+
 #[derive(Helper)]
 pub struct UnifiedCompleter {
     engine: Arc<CompletionEngine>,
+    argument_cache: Arc<CompletionCache>,
     runtime_handle: tokio::runtime::Handle,
     highlighter: MatchingBracketHighlighter,
     hinter: HistoryHinter,
@@ -29,6 +35,9 @@ pub struct UnifiedCompleter {
 
 impl UnifiedCompleter {
     pub fn new(client: Arc<GrpcClient>) -> Self {
+        let argument_provider = ArgumentCompletionProvider::new(client.clone());
+        let argument_cache = argument_provider.cache();
+        
         // Build the completion engine with all providers
         let engine = CompletionEngineBuilder::new()
             .with_config(CompletionConfig {
@@ -42,17 +51,35 @@ impl UnifiedCompleter {
             })
             .with_provider(Box::new(TuiCommandCompletionProvider::new()))
             .with_provider(Box::new(UserCompletionProvider::new(client.clone())))
+            .with_provider(Box::new(PipelineCompletionProvider::new(client.clone())))
+            .with_provider(Box::new(argument_provider))
             .with_provider(Box::new(CommandCompletionProvider::new(client.clone())))
             .with_provider(Box::new(EmoteCompletionProvider::new(client.clone())))
             .build();
         
         Self {
             engine: Arc::new(engine),
+            argument_cache,
             runtime_handle: tokio::runtime::Handle::current(),
             highlighter: MatchingBracketHighlighter::new(),
             hinter: HistoryHinter::new(),
         }
     }
+    
+    /// Drop cached server data that `line` may have changed, so the next Tab
+    /// press reflects it instead of waiting for the TTL.
+    pub fn invalidate_after(&self, line: &str) {
+        for key in ArgumentKind::invalidated_by(line) {
+            self.argument_cache.invalidate(key);
+        }
+        
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["user", "add" | "remove" | "merge", ..] => self.engine.invalidate_cache("users:"),
+            ["pipeline", "create" | "import" | "delete", ..] => self.engine.invalidate_cache("pipelines:"),
+            _ => {}
+        }
+    }
 }
 
 impl Completer for UnifiedCompleter {
@@ -68,9 +95,15 @@ impl Completer for UnifiedCompleter {
             pos,
         );
         
-        // Get completions from the engine
-        let items = self.runtime_handle.block_on(async move {
-            engine.get_completions(&context).await
+        // Get completions from the engine. rustyline calls us synchronously from
+        // inside the runtime, so hand this worker thread over before blocking.
+        let handle = self.runtime_handle.clone();
+        let items = tokio::task::block_in_place(|| {
+            handle.block_on(async move {
+                tokio::time::timeout(COMPLETION_TIMEOUT, engine.get_completions(&context))
+                    .await
+                    .unwrap_or_default()
+            })
         });
         
         // Convert to rustyline pairs
@@ -80,14 +113,14 @@ impl Completer for UnifiedCompleter {
                 let display = if let Some(desc) = item.description {
                     format!(
                         "{}{:<20} {}",
-                        if item.icon.is_some() { format!("{} ", item.icon.unwrap()) } else { String::new() },
+                        item.icon.map(|icon| format!("{} ", icon)).unwrap_or_default(),
                         item.display,
                         desc
                     )
                 } else {
                     format!(
                         "{}{}",
-                        if item.icon.is_some() { format!("{} ", item.icon.unwrap()) } else { String::new() },
+                        item.icon.map(|icon| format!("{} ", icon)).unwrap_or_default(),
                         item.display
                     )
                 };
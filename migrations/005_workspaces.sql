@@ -0,0 +1,71 @@
+-- 005_workspaces.sql
+-- Workspaces: scope commands, redeems, bot config and credentials to a broadcaster
+-- so one server can manage several channels independently.
+-- Existing rows move into the built-in 'default' workspace.
+
+---------------------------------------------------------------------------
+-- WORKSPACES
+---------------------------------------------------------------------------
+
+CREATE TABLE workspaces (
+    workspace_id    UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name            TEXT NOT NULL UNIQUE,
+    display_name    TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT workspace_name_check CHECK (name ~ '^[a-z0-9][a-z0-9_-]*$')
+);
+
+INSERT INTO workspaces (workspace_id, name, display_name)
+VALUES ('00000000-0000-0000-0000-000000000000', 'default', 'Default');
+
+---------------------------------------------------------------------------
+-- COMMANDS
+---------------------------------------------------------------------------
+
+ALTER TABLE commands
+    ADD COLUMN workspace_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES workspaces(workspace_id) ON DELETE CASCADE;
+
+ALTER TABLE commands DROP CONSTRAINT commands_unique;
+ALTER TABLE commands ADD CONSTRAINT commands_unique UNIQUE(workspace_id, platform, command_name);
+
+CREATE INDEX idx_commands_workspace ON commands(workspace_id);
+
+---------------------------------------------------------------------------
+-- REDEEMS
+---------------------------------------------------------------------------
+
+ALTER TABLE redeems
+    ADD COLUMN workspace_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES workspaces(workspace_id) ON DELETE CASCADE;
+
+ALTER TABLE redeems DROP CONSTRAINT redeem_unique;
+ALTER TABLE redeems ADD CONSTRAINT redeem_unique UNIQUE(workspace_id, platform, reward_id);
+ALTER TABLE redeems DROP CONSTRAINT redeems_internal_name_key;
+ALTER TABLE redeems ADD CONSTRAINT redeems_internal_name_key UNIQUE(workspace_id, internal_name);
+
+CREATE INDEX idx_redeems_workspace ON redeems(workspace_id);
+
+---------------------------------------------------------------------------
+-- BOT CONFIG
+---------------------------------------------------------------------------
+
+ALTER TABLE bot_config
+    ADD COLUMN workspace_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES workspaces(workspace_id) ON DELETE CASCADE;
+
+ALTER TABLE bot_config DROP CONSTRAINT bot_config_pkey;
+ALTER TABLE bot_config ADD PRIMARY KEY (workspace_id, config_key);
+
+---------------------------------------------------------------------------
+-- PLATFORM CREDENTIALS
+---------------------------------------------------------------------------
+
+-- A platform account belongs to exactly one workspace, so the
+-- (platform, user_id) uniqueness stays global.
+ALTER TABLE platform_credentials
+    ADD COLUMN workspace_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES workspaces(workspace_id) ON DELETE CASCADE;
+
+CREATE INDEX idx_platform_credentials_workspace ON platform_credentials(workspace_id);
@@ -0,0 +1,39 @@
+-- 006_api_tokens.sql
+-- Role-bound API tokens for gRPC clients, and an audit log of admin actions.
+
+---------------------------------------------------------------------------
+-- API TOKENS
+---------------------------------------------------------------------------
+
+-- Only a SHA-256 hash of each token is stored; the token itself is shown once.
+CREATE TABLE api_tokens (
+    token_id        UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name            TEXT NOT NULL UNIQUE,
+    role            TEXT NOT NULL,
+    token_hash      TEXT NOT NULL UNIQUE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at    TIMESTAMPTZ,
+    revoked_at      TIMESTAMPTZ,
+
+    CONSTRAINT api_token_role_check CHECK (role IN ('admin', 'moderator', 'readonly'))
+);
+
+---------------------------------------------------------------------------
+-- AUDIT LOG
+---------------------------------------------------------------------------
+
+-- Admin-level calls (allowed or not) and every denied call
+CREATE TABLE grpc_audit_log (
+    audit_id        BIGSERIAL PRIMARY KEY,
+    token_id        UUID REFERENCES api_tokens(token_id) ON DELETE SET NULL,
+    client_name     TEXT,
+    role            TEXT,
+    method          TEXT NOT NULL,
+    workspace       TEXT,
+    allowed         BOOLEAN NOT NULL,
+    reason          TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_grpc_audit_log_created ON grpc_audit_log(created_at DESC);
+CREATE INDEX idx_grpc_audit_log_token ON grpc_audit_log(token_id);
@@ -0,0 +1,21 @@
+-- 007_chat_archive.sql
+-- Full-text search over the chat archive and the !lastseen built-in.
+
+---------------------------------------------------------------------------
+-- SEARCH
+---------------------------------------------------------------------------
+
+-- 'simple' config: chat is multilingual and full of names/emotes, so no stemming
+ALTER TABLE chat_messages
+    ADD COLUMN search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('simple', message_text)) STORED;
+
+CREATE INDEX idx_chat_messages_search ON chat_messages USING GIN(search_vector);
+
+---------------------------------------------------------------------------
+-- BUILT-IN COMMANDS
+---------------------------------------------------------------------------
+
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name)
+VALUES ('twitch', 'lastseen', 'viewer', true, 'builtin')
+ON CONFLICT DO NOTHING;
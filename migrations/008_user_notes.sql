@@ -0,0 +1,37 @@
+-- 008_user_notes.sql
+-- Timestamped moderator notes and a cross-platform moderation history per user.
+
+---------------------------------------------------------------------------
+-- NOTES
+---------------------------------------------------------------------------
+
+CREATE TABLE user_notes (
+    note_id     UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id     UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    author      TEXT NOT NULL,
+    note_text   TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_user_notes_user_id ON user_notes(user_id, created_at DESC);
+
+---------------------------------------------------------------------------
+-- MODERATION HISTORY
+---------------------------------------------------------------------------
+
+CREATE TABLE user_moderation_actions (
+    action_id         UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id           UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    platform          TEXT NOT NULL,
+    channel           TEXT NOT NULL DEFAULT '',
+    action_type       TEXT NOT NULL,
+    reason            TEXT,
+    -- Only set for timeouts
+    duration_seconds  INTEGER,
+    moderator         TEXT NOT NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT user_moderation_action_type CHECK (action_type IN ('timeout', 'ban', 'unban', 'warning'))
+);
+
+CREATE INDEX idx_user_moderation_actions_user_id ON user_moderation_actions(user_id, created_at DESC);
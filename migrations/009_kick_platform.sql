@@ -0,0 +1,24 @@
+-- 009_kick_platform.sql
+-- Kick.com as a chat platform: allow 'kick' wherever platforms are validated.
+
+ALTER TABLE platform_identities DROP CONSTRAINT platform_check;
+ALTER TABLE platform_identities ADD CONSTRAINT platform_check
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick'));
+
+ALTER TABLE platform_credentials DROP CONSTRAINT platform_cred_check;
+ALTER TABLE platform_credentials ADD CONSTRAINT platform_cred_check
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick'));
+
+ALTER TABLE platform_config DROP CONSTRAINT platform_config_check;
+ALTER TABLE platform_config ADD CONSTRAINT platform_config_check
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick'));
+
+ALTER TABLE event_type_registry DROP CONSTRAINT platform_event_check;
+ALTER TABLE event_type_registry ADD CONSTRAINT platform_event_check
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick', 'system'));
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('kick', 'chat', 'message.create', 'Chat message received'),
+    ('kick', 'channel', 'kick.follow', 'User followed the channel'),
+    ('kick', 'channel', 'kick.subscription', 'User subscribed or resubscribed'),
+    ('kick', 'channel', 'kick.subscription.gift', 'User gifted subscriptions');
@@ -0,0 +1,7 @@
+-- 010_social_posting.sql
+-- Pipeline action that posts a status to Bluesky and/or Mastodon.
+-- Credentials are bot_config settings under social.* (see `config schema social`).
+
+INSERT INTO event_handler_registry (handler_type, handler_name, handler_category, description, parameters, is_builtin) VALUES
+    ('action', 'post_status', 'social', 'Post a status to Bluesky/Mastodon',
+     '{"networks": {"type": "array", "items": {"type": "string", "enum": ["bluesky", "mastodon"]}, "default": ["bluesky", "mastodon"]}, "message_template": {"type": "string", "default": "{user} is live: {title} {link}"}}', true);
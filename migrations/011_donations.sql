@@ -0,0 +1,29 @@
+-- 011_donations.sql
+-- Tips received through Streamlabs and StreamElements, for leaderboards and history.
+
+CREATE TABLE donations (
+    donation_id    UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    source         TEXT NOT NULL,
+    external_id    TEXT,
+    donor_name     TEXT NOT NULL,
+    message        TEXT,
+    amount         DOUBLE PRECISION NOT NULL,
+    currency       TEXT NOT NULL,
+    -- amount converted to base_currency; NULL when no exchange rate was configured
+    amount_base    DOUBLE PRECISION,
+    base_currency  TEXT NOT NULL,
+    received_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT donation_source_check CHECK (source IN ('streamlabs', 'streamelements')),
+    CONSTRAINT donation_external_unique UNIQUE (source, external_id)
+);
+
+CREATE INDEX idx_donations_received_at ON donations(received_at DESC);
+CREATE INDEX idx_donations_donor ON donations(LOWER(donor_name));
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('system', 'donation', 'donation', 'Tip received through Streamlabs or StreamElements');
+
+INSERT INTO event_handler_registry (handler_type, handler_name, handler_category, description, parameters, is_builtin) VALUES
+    ('filter', 'donation_amount_filter', 'donation', 'Filter donations by amount in the base currency',
+     '{"min_amount": {"type": "number", "default": 0}, "max_amount": {"type": "number"}}', true);
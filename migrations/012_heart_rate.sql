@@ -0,0 +1,5 @@
+-- 012_heart_rate.sql
+-- Heart rate readings from Pulsoid/HypeRate, available as a pipeline trigger.
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('system', 'heart_rate', 'heart_rate', 'Live heart rate (BPM) changed');
@@ -0,0 +1,34 @@
+-- 013_stream_markers.sql
+-- Helix stream markers placed by the bot, and the chapter list exported per VOD
+-- when the stream ends.
+
+CREATE TABLE stream_markers (
+    marker_id         UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    broadcaster_id    TEXT NOT NULL,
+    stream_id         TEXT NOT NULL,
+    twitch_marker_id  TEXT,
+    position_seconds  INTEGER NOT NULL,
+    description       TEXT NOT NULL,
+    source            TEXT NOT NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT stream_marker_source_check CHECK (source IN ('raid', 'redeem', 'command', 'scene', 'manual'))
+);
+
+CREATE INDEX idx_stream_markers_stream ON stream_markers(stream_id, position_seconds);
+
+CREATE TABLE vod_chapters (
+    stream_id       TEXT PRIMARY KEY,
+    broadcaster_id  TEXT NOT NULL,
+    video_id        TEXT,
+    video_url       TEXT,
+    title           TEXT NOT NULL,
+    chapters        TEXT NOT NULL,
+    marker_count    INTEGER NOT NULL DEFAULT 0,
+    exported_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_vod_chapters_video ON vod_chapters(video_id);
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('obs', 'scene', 'obs.scene_changed', 'The program scene of a connected OBS instance changed');
@@ -0,0 +1,41 @@
+-- 014_giveaways.sql
+-- Giveaways and their entries, persisted so a restart mid-giveaway keeps every entry.
+
+CREATE TABLE giveaways (
+    giveaway_id           UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    title                 TEXT NOT NULL,
+    channel               TEXT NOT NULL,
+    keyword               TEXT,
+    points_cost           BIGINT NOT NULL DEFAULT 0,
+    reward_id             TEXT,
+    sub_only              BOOLEAN NOT NULL DEFAULT false,
+    min_follow_days       INTEGER NOT NULL DEFAULT 0,
+    exclude_winners_days  INTEGER NOT NULL DEFAULT 0,
+    sub_weight            INTEGER NOT NULL DEFAULT 1,
+    status                TEXT NOT NULL DEFAULT 'open',
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at             TIMESTAMPTZ,
+
+    CONSTRAINT giveaway_status_check CHECK (status IN ('open', 'closed', 'cancelled')),
+    CONSTRAINT giveaway_entry_method_check CHECK (keyword IS NOT NULL OR points_cost > 0),
+    CONSTRAINT giveaway_sub_weight_check CHECK (sub_weight >= 1)
+);
+
+CREATE INDEX idx_giveaways_status ON giveaways(status, created_at DESC);
+
+CREATE TABLE giveaway_entries (
+    entry_id       UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    giveaway_id    UUID NOT NULL REFERENCES giveaways(giveaway_id) ON DELETE CASCADE,
+    user_id        UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    username       TEXT NOT NULL,
+    is_subscriber  BOOLEAN NOT NULL DEFAULT false,
+    weight         INTEGER NOT NULL DEFAULT 1,
+    is_winner      BOOLEAN NOT NULL DEFAULT false,
+    rerolled       BOOLEAN NOT NULL DEFAULT false,
+    entered_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    won_at         TIMESTAMPTZ,
+
+    CONSTRAINT giveaway_entry_unique UNIQUE (giveaway_id, user_id)
+);
+
+CREATE INDEX idx_giveaway_entries_winners ON giveaway_entries(user_id, won_at) WHERE is_winner;
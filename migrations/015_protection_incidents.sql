@@ -0,0 +1,20 @@
+-- 015_protection_incidents.sql
+-- Raid-defense lockdowns: what tripped them, what was switched on, and the
+-- chat messages captured while they lasted.
+
+CREATE TABLE protection_incidents (
+    incident_id  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    channel      TEXT NOT NULL,
+    trigger      TEXT NOT NULL,
+    details      TEXT NOT NULL DEFAULT '',
+    actions      JSONB NOT NULL DEFAULT '[]'::jsonb,
+    messages     JSONB NOT NULL DEFAULT '[]'::jsonb,
+    started_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ended_at     TIMESTAMPTZ,
+    ended_by     TEXT,
+
+    CONSTRAINT protection_incident_trigger_check
+        CHECK (trigger IN ('message_rate', 'identical_messages', 'new_accounts', 'manual'))
+);
+
+CREATE INDEX idx_protection_incidents_started ON protection_incidents(started_at DESC);
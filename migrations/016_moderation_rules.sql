@@ -0,0 +1,24 @@
+-- 016_moderation_rules.sql
+-- Chat moderation rules (link allowlists, phrase and regex blocklists) with
+-- per-rule hit counters.
+
+CREATE TABLE moderation_rules (
+    rule_id       UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name          TEXT NOT NULL UNIQUE,
+    kind          TEXT NOT NULL,
+    pattern       TEXT NOT NULL DEFAULT '',
+    severity      TEXT NOT NULL DEFAULT 'delete',
+    timeout_secs  INTEGER NOT NULL DEFAULT 600,
+    permit_subs   BOOLEAN NOT NULL DEFAULT false,
+    permit_vips   BOOLEAN NOT NULL DEFAULT false,
+    permit_mods   BOOLEAN NOT NULL DEFAULT true,
+    enabled       BOOLEAN NOT NULL DEFAULT true,
+    hit_count     BIGINT NOT NULL DEFAULT 0,
+    last_hit_at   TIMESTAMPTZ,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT moderation_rule_kind_check CHECK (kind IN ('link', 'phrase', 'regex')),
+    CONSTRAINT moderation_rule_severity_check CHECK (severity IN ('delete', 'timeout', 'ban')),
+    CONSTRAINT moderation_rule_timeout_check CHECK (timeout_secs BETWEEN 1 AND 1209600)
+);
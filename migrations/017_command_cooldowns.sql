@@ -0,0 +1,9 @@
+-- 017_command_cooldowns.sql
+-- Per-user and per-role cooldowns next to the global one, and a flag letting
+-- moderators skip them. role_cooldowns maps a chat role to seconds, e.g.
+-- {"subscriber": 10, "vip": 5}.
+
+ALTER TABLE commands
+    ADD COLUMN user_cooldown_seconds INT NOT NULL DEFAULT 0 CHECK (user_cooldown_seconds >= 0),
+    ADD COLUMN role_cooldowns        JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ADD COLUMN cooldown_bypass_mods  BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,10 @@
+-- 018_command_reply_privately.sql
+-- Commands can answer with a whisper instead of a chat message, and the
+-- twitch_message pipeline action gains the same option.
+
+ALTER TABLE commands
+    ADD COLUMN reply_privately BOOLEAN NOT NULL DEFAULT false;
+
+UPDATE event_handler_registry
+SET parameters = parameters || '{"reply_privately": {"type": "boolean", "default": false}}'::jsonb
+WHERE handler_type = 'action' AND handler_name = 'twitch_message';
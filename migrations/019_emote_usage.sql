@@ -0,0 +1,21 @@
+-- 019_emote_usage.sql
+-- Daily emote usage per channel (Twitch, 7TV, BTTV and FFZ emotes) and the
+-- !emotestats built-in.
+
+CREATE TABLE emote_usage_daily (
+    channel     TEXT NOT NULL,
+    day         DATE NOT NULL,
+    provider    TEXT NOT NULL,
+    emote_id    TEXT NOT NULL,
+    emote_name  TEXT NOT NULL,
+    uses        BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (channel, day, provider, emote_id),
+    CONSTRAINT emote_usage_provider_check CHECK (provider IN ('twitch', '7tv', 'bttv', 'ffz'))
+);
+
+CREATE INDEX idx_emote_usage_daily_channel_day ON emote_usage_daily(channel, day);
+
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name)
+VALUES ('twitch', 'emotestats', 'viewer', true, 'builtin')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,20 @@
+-- 020_posted_clips.sql
+-- Twitch clips already posted to Discord, so polling never posts one twice,
+-- and the !clipit built-in.
+
+CREATE TABLE posted_clips (
+    clip_id         TEXT PRIMARY KEY,
+    broadcaster_id  TEXT NOT NULL,
+    title           TEXT NOT NULL DEFAULT '',
+    creator_name    TEXT NOT NULL DEFAULT '',
+    url             TEXT NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL,
+    posted_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    requested_by    TEXT
+);
+
+CREATE INDEX idx_posted_clips_posted_at ON posted_clips(posted_at DESC);
+
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name, cooldown_seconds)
+VALUES ('twitch', 'clipit', 'viewer', true, 'builtin', 30)
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,20 @@
+-- 021_localization.sql
+-- Response templates per language, on top of the packs bundled with the bot
+-- (maowbot-core/locales/*.json), and the language each channel is answered in.
+
+CREATE TABLE language_strings (
+    language     TEXT NOT NULL,
+    message_key  TEXT NOT NULL,
+    template     TEXT NOT NULL,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (language, message_key)
+);
+
+CREATE TABLE channel_languages (
+    platform  TEXT NOT NULL,
+    channel   TEXT NOT NULL,
+    language  TEXT NOT NULL,
+
+    PRIMARY KEY (platform, channel)
+);
@@ -0,0 +1,26 @@
+-- 022_redeem_schedules.sql
+-- Rules that enable, disable or reprice a channel point reward while a
+-- condition holds: a weekly time window, a stream category or a hype train.
+
+CREATE TABLE redeem_schedules (
+    schedule_id      UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    redeem_id        UUID NOT NULL REFERENCES redeems(redeem_id) ON DELETE CASCADE,
+    name             TEXT NOT NULL,
+    condition_kind   TEXT NOT NULL,
+    -- "mon-fri 18:00-22:00 Europe/Berlin" for time windows, the category name for categories
+    condition_value  TEXT NOT NULL DEFAULT '',
+    action           TEXT NOT NULL,
+    -- The cost for "set_cost", the factor for "multiply_cost"
+    amount           DOUBLE PRECISION,
+    -- Higher priorities are applied later, so they win
+    priority         INTEGER NOT NULL DEFAULT 0,
+    enabled          BOOLEAN NOT NULL DEFAULT true,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT redeem_schedule_name_unique UNIQUE (redeem_id, name),
+    CONSTRAINT redeem_schedule_condition_check CHECK (condition_kind IN ('time', 'category', 'hype_train')),
+    CONSTRAINT redeem_schedule_action_check CHECK (action IN ('enable', 'disable', 'set_cost', 'multiply_cost'))
+);
+
+CREATE INDEX idx_redeem_schedules_redeem ON redeem_schedules(redeem_id);
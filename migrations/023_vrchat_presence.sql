@@ -0,0 +1,11 @@
+-- 023_vrchat_presence.sql
+-- Friends joining or leaving the streamer's VRChat instance, available as
+-- pipeline triggers, and the !whoshere built-in.
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('vrchat', 'presence', 'vrchat.friend_joined', 'A friend joined the streamer''s VRChat instance'),
+    ('vrchat', 'presence', 'vrchat.friend_left', 'A friend left the streamer''s VRChat instance');
+
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name, cooldown_seconds)
+VALUES ('twitch', 'whoshere', 'viewer', true, 'builtin', 15)
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,8 @@
+-- VRChat group activity (join requests, approvals, announcements), available
+-- as pipeline triggers.
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('vrchat', 'group', 'vrchat.group_join_request', 'Someone asked to join the streamer''s VRChat group'),
+    ('vrchat', 'group', 'vrchat.group_request_approved', 'A VRChat group join request was approved through the bot'),
+    ('vrchat', 'group', 'vrchat.group_request_rejected', 'A VRChat group join request was rejected through the bot'),
+    ('vrchat', 'group', 'vrchat.group_post', 'The bot posted an announcement to the VRChat group');
@@ -0,0 +1,6 @@
+-- 025_command_reply_to_message.sql
+-- Commands can answer as a reply to the triggering message (a Twitch reply
+-- thread or a Discord message reference) instead of a plain chat line.
+
+ALTER TABLE commands
+    ADD COLUMN reply_to_message BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,10 @@
+-- 026_command_usage_timing.sql
+-- How long each command's handler took, for `command stats` latency
+-- percentiles (failures already land in error_message), plus the metadata
+-- column the usage repository writes.
+
+ALTER TABLE command_usage
+    ADD COLUMN duration_ms INTEGER,
+    ADD COLUMN metadata JSONB;
+
+CREATE INDEX idx_command_usage_command_time ON command_usage(command_id, executed_at DESC);
@@ -0,0 +1,28 @@
+-- 027_pipeline_throttles.sql
+-- Rate limits per pipeline and per user. Runs are counted in
+-- pipeline_throttle_hits rather than in memory so a restart doesn't hand
+-- spammers a fresh allowance.
+
+CREATE TABLE pipeline_throttles (
+    pipeline_id             UUID PRIMARY KEY REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    cooldown_seconds        INT NOT NULL DEFAULT 0,
+    user_cooldown_seconds   INT NOT NULL DEFAULT 0,
+    max_executions          INT NOT NULL DEFAULT 0,
+    max_executions_per_user INT NOT NULL DEFAULT 0,
+    window_seconds          INT NOT NULL DEFAULT 60,
+    updated_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT pipeline_throttles_non_negative CHECK (
+        cooldown_seconds >= 0 AND user_cooldown_seconds >= 0 AND
+        max_executions >= 0 AND max_executions_per_user >= 0 AND window_seconds >= 0
+    )
+);
+
+-- scope_key is '' for the pipeline as a whole, otherwise '<platform>:<user>'
+CREATE TABLE pipeline_throttle_hits (
+    pipeline_id UUID NOT NULL REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    scope_key   TEXT NOT NULL,
+    hit_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_pipeline_throttle_hits_scope ON pipeline_throttle_hits(pipeline_id, scope_key, hit_at DESC);
@@ -0,0 +1,13 @@
+-- 028_audit_changes.sql
+-- The gRPC audit log now also records every state-changing call once it has
+-- finished: what it changed (as noted by the service), the values before and
+-- after, and how the call ended. Access entries from 006 keep these empty.
+
+ALTER TABLE grpc_audit_log
+    ADD COLUMN target       TEXT,
+    ADD COLUMN before_value JSONB,
+    ADD COLUMN after_value  JSONB,
+    ADD COLUMN outcome      TEXT;
+
+CREATE INDEX idx_grpc_audit_log_method ON grpc_audit_log(method);
+CREATE INDEX idx_grpc_audit_log_target ON grpc_audit_log(target) WHERE target IS NOT NULL;
@@ -0,0 +1,23 @@
+-- 029_chat_activity.sql
+-- Message counts per channel in 5-minute buckets, for activity heatmaps and
+-- finding a stream's peak moments. Kept separately from chat_messages so the
+-- counts survive chat retention purges.
+
+CREATE TABLE chat_activity (
+    platform      TEXT NOT NULL,
+    channel       TEXT NOT NULL,
+    bucket_start  TIMESTAMPTZ NOT NULL,
+    message_count INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (platform, channel, bucket_start)
+);
+
+CREATE INDEX idx_chat_activity_bucket ON chat_activity(bucket_start);
+
+-- Backfill from the messages still in the archive
+INSERT INTO chat_activity (platform, channel, bucket_start, message_count)
+SELECT platform,
+       channel,
+       to_timestamp(floor(extract(epoch FROM timestamp) / 300) * 300) AS bucket_start,
+       COUNT(*)
+FROM chat_messages
+GROUP BY 1, 2, 3;
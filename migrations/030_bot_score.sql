@@ -0,0 +1,11 @@
+-- 030_bot_score.sql
+-- Bot detection scores new Twitch chatters (account age, follow timing,
+-- follow-bot advertising, message entropy) and keeps the result, with the
+-- signals behind it, next to the rest of the user's analysis.
+
+ALTER TABLE user_analysis
+    ADD COLUMN bot_score     FLOAT4 NOT NULL DEFAULT 0.0,
+    ADD COLUMN bot_signals   TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN bot_scored_at TIMESTAMPTZ;
+
+CREATE INDEX idx_user_analysis_bot_score ON user_analysis(bot_score) WHERE bot_score > 0;
@@ -0,0 +1,21 @@
+-- 031_analysis_recompute.sql
+-- Runs of the on-demand user analysis recompute (`user analyze --all`). Users
+-- are processed in user_id order, so last_user_id is the checkpoint an
+-- interrupted or cancelled run resumes from.
+
+CREATE TABLE analysis_recompute_runs (
+    run_id          UUID PRIMARY KEY,
+    since           TIMESTAMPTZ NOT NULL,
+    until           TIMESTAMPTZ NOT NULL,
+    -- running, finished, cancelled, interrupted or failed
+    status          TEXT NOT NULL,
+    total_users     INT NOT NULL DEFAULT 0,
+    processed_users INT NOT NULL DEFAULT 0,
+    last_user_id    UUID,
+    error           TEXT,
+    started_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at     TIMESTAMPTZ
+);
+
+CREATE INDEX idx_analysis_recompute_runs_started ON analysis_recompute_runs(started_at DESC);
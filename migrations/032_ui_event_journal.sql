@@ -0,0 +1,10 @@
+-- 032_ui_event_journal.sql
+-- The event journal (bot_events) now records what the SubscribeEvents stream
+-- sends to UI clients. seq numbers events in the order they were sent, so a
+-- client that reconnects can ask for everything after the last one it saw.
+
+ALTER TABLE bot_events ADD COLUMN seq BIGINT;
+-- chat, alert, connection or osc
+ALTER TABLE bot_events ADD COLUMN kind TEXT;
+
+CREATE UNIQUE INDEX idx_bot_events_seq ON bot_events(seq);
@@ -0,0 +1,23 @@
+-- 033_midi_mappings.sql
+-- MIDI notes and controllers bound to bot actions: avatar parameters, OBS
+-- scenes and chat macros. Each note or controller drives at most one action.
+
+CREATE TABLE midi_mappings (
+    mapping_id    UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name          TEXT NOT NULL UNIQUE,
+    trigger_kind  TEXT NOT NULL,
+    channel       INTEGER NOT NULL,
+    number        INTEGER NOT NULL,
+    action        TEXT NOT NULL,
+    target        TEXT NOT NULL,
+    argument      TEXT NOT NULL DEFAULT '',
+    enabled       BOOLEAN NOT NULL DEFAULT true,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT midi_mapping_trigger_unique UNIQUE (trigger_kind, channel, number),
+    CONSTRAINT midi_mapping_trigger_check CHECK (trigger_kind IN ('note', 'cc')),
+    CONSTRAINT midi_mapping_channel_check CHECK (channel BETWEEN 1 AND 16),
+    CONSTRAINT midi_mapping_number_check CHECK (number BETWEEN 0 AND 127),
+    CONSTRAINT midi_mapping_action_check CHECK (action IN ('osc', 'obs_scene', 'chat'))
+);
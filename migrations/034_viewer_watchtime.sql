@@ -0,0 +1,26 @@
+-- 034_viewer_watchtime.sql
+-- Time each viewer has spent in a Twitch channel's chat while it was live,
+-- counted from the Helix chatters list, plus the presence pipeline trigger
+-- and the !lurkers and !watchtime built-ins.
+
+CREATE TABLE viewer_watchtime (
+    channel         TEXT NOT NULL,
+    twitch_user_id  TEXT NOT NULL,
+    user_login      TEXT NOT NULL,
+    display_name    TEXT NOT NULL,
+    watch_seconds   BIGINT NOT NULL DEFAULT 0,
+    first_seen      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (channel, twitch_user_id)
+);
+
+CREATE INDEX idx_viewer_watchtime_login ON viewer_watchtime(channel, user_login);
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'presence', 'twitch.chatters_changed', 'Viewers joined or left the broadcaster''s chat');
+
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name, cooldown_seconds)
+VALUES ('twitch', 'lurkers', 'viewer', true, 'builtin', 15),
+       ('twitch', 'watchtime', 'viewer', true, 'builtin', 5)
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,10 @@
+-- 035_twitch_schedule.sql
+-- The !schedule built-in and the pipeline trigger fired before a scheduled
+-- Twitch stream starts.
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'schedule', 'twitch.schedule_starting_soon', 'A scheduled stream starts soon and the channel isn''t live yet');
+
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name, cooldown_seconds)
+VALUES ('twitch', 'schedule', 'viewer', true, 'builtin', 15)
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,9 @@
+-- 036_hype_moments.sql
+-- Hype moments: their stream markers and the pipeline trigger.
+
+ALTER TABLE stream_markers DROP CONSTRAINT stream_marker_source_check;
+ALTER TABLE stream_markers ADD CONSTRAINT stream_marker_source_check
+    CHECK (source IN ('raid', 'redeem', 'command', 'scene', 'manual', 'hype'));
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'stream', 'twitch.hype_moment', 'Chat speed, emote spam, bits or subs spiked while live');
@@ -0,0 +1,28 @@
+-- 037_responders.sql
+-- Chat responders: regex or keyword triggers that answer in chat, separate
+-- from prefix commands, with chance and cooldown controls.
+
+CREATE TABLE responders (
+    responder_id       UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name               TEXT NOT NULL UNIQUE,
+    match_kind         TEXT NOT NULL,
+    pattern            TEXT NOT NULL,
+    responses          TEXT[] NOT NULL DEFAULT '{}',
+    platform           TEXT,
+    channel            TEXT,
+    chance_percent     INTEGER NOT NULL DEFAULT 100,
+    cooldown_secs      INTEGER NOT NULL DEFAULT 30,
+    user_cooldown_secs INTEGER NOT NULL DEFAULT 0,
+    enabled            BOOLEAN NOT NULL DEFAULT true,
+    hit_count          BIGINT NOT NULL DEFAULT 0,
+    last_hit_at        TIMESTAMPTZ,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT responder_match_kind_check CHECK (match_kind IN ('regex', 'any', 'all')),
+    CONSTRAINT responder_chance_check CHECK (chance_percent BETWEEN 1 AND 100),
+    CONSTRAINT responder_cooldown_check CHECK (cooldown_secs >= 0 AND user_cooldown_secs >= 0)
+);
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('system', 'chat', 'responder.triggered', 'A chat responder matched a message and passed its chance and cooldowns');
@@ -0,0 +1,27 @@
+-- 038_osc_chat_controls.sql
+-- Avatar parameters chat can drive with !toggle and !set, each with its own
+-- minimum role, value bounds and cooldown.
+
+CREATE TABLE osc_chat_controls (
+    id               SERIAL PRIMARY KEY,
+    name             TEXT NOT NULL UNIQUE,
+    parameter_name   TEXT NOT NULL,
+    parameter_type   TEXT NOT NULL,
+    min_role         TEXT NOT NULL DEFAULT 'moderator',
+    min_value        REAL,
+    max_value        REAL,
+    cooldown_seconds INTEGER NOT NULL DEFAULT 0,
+    enabled          BOOLEAN NOT NULL DEFAULT true,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT osc_chat_control_type_check CHECK (parameter_type IN ('bool', 'int', 'float')),
+    CONSTRAINT osc_chat_control_role_check CHECK (min_role IN ('everyone', 'subscriber', 'vip', 'moderator', 'broadcaster')),
+    CONSTRAINT osc_chat_control_cooldown_check CHECK (cooldown_seconds >= 0)
+);
+
+-- Each control checks its own role, so the commands are open to every viewer
+INSERT INTO commands (platform, command_name, min_role, is_active, plugin_name, cooldown_seconds)
+VALUES ('twitch', 'toggle', 'viewer', true, 'builtin', 0),
+       ('twitch', 'set', 'viewer', true, 'builtin', 0)
+ON CONFLICT DO NOTHING;
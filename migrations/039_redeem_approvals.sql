@@ -0,0 +1,28 @@
+-- 039_redeem_approvals.sql
+-- Redeems can wait for the streamer's approval before running. Pending
+-- redemptions are queued here; denied and expired ones are refunded.
+
+ALTER TABLE redeems ADD COLUMN requires_approval BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TABLE redeem_approvals (
+    approval_id  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    redeem_id    UUID NOT NULL REFERENCES redeems(redeem_id) ON DELETE CASCADE,
+    reward_name  TEXT NOT NULL,
+    platform     TEXT NOT NULL,
+    channel      TEXT NOT NULL,
+    user_id      UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    user_name    TEXT NOT NULL,
+    user_input   TEXT NOT NULL DEFAULT '',
+    redemption   JSONB NOT NULL,
+    status       TEXT NOT NULL DEFAULT 'pending',
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at   TIMESTAMPTZ,
+    decided_by   TEXT,
+
+    CONSTRAINT redeem_approval_status_check CHECK (status IN ('pending', 'approved', 'denied', 'expired'))
+);
+
+CREATE INDEX idx_redeem_approvals_pending ON redeem_approvals (requested_at) WHERE status = 'pending';
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'channel_points', 'redeem.approval', 'A redemption was queued for approval, approved, denied or expired');
@@ -0,0 +1,28 @@
+-- 040_alert_rules.sql
+-- Alert rules for operational problems (platform disconnects, credential
+-- failures, failing pipelines, low disk): which alerts go where, with dedupe
+-- windows and quiet hours. Starts with one rule showing warnings in the GUI.
+
+CREATE TABLE alert_rules (
+    rule_id      UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name         TEXT NOT NULL UNIQUE,
+    kinds        TEXT[] NOT NULL DEFAULT '{}',
+    min_severity TEXT NOT NULL DEFAULT 'warning',
+    route        TEXT NOT NULL,
+    target       TEXT,
+    dedupe_secs  INTEGER NOT NULL DEFAULT 900,
+    quiet_hours  TEXT,
+    enabled      BOOLEAN NOT NULL DEFAULT true,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT alert_rule_severity_check CHECK (min_severity IN ('info', 'warning', 'critical')),
+    CONSTRAINT alert_rule_route_check CHECK (route IN ('discord_dm', 'chat', 'desktop')),
+    CONSTRAINT alert_rule_dedupe_check CHECK (dedupe_secs >= 0)
+);
+
+INSERT INTO alert_rules (name, min_severity, route, dedupe_secs) VALUES
+    ('desktop', 'warning', 'desktop', 900);
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('system', 'ops', 'ops.alert', 'An operational problem was detected and routed by the alert rules');
@@ -0,0 +1,24 @@
+-- 041_moderation_escalation.sql
+-- Escalation ladder for moderation rules: rules with a category share a
+-- ladder (warn, then timeout, then ban by default), and the offenses on it
+-- are the moderation actions recorded in that category. A pardon starts the
+-- ladder over.
+
+ALTER TABLE moderation_rules
+    ADD COLUMN category TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE user_moderation_actions
+    ADD COLUMN category TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE user_moderation_actions
+    DROP CONSTRAINT user_moderation_action_type;
+ALTER TABLE user_moderation_actions
+    ADD CONSTRAINT user_moderation_action_type
+        CHECK (action_type IN ('timeout', 'ban', 'unban', 'warning', 'pardon'));
+
+CREATE INDEX idx_user_moderation_actions_category
+    ON user_moderation_actions(user_id, category, created_at DESC)
+    WHERE category <> '';
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'moderation', 'moderation.escalated', 'The moderation ladder timed out or banned a repeat offender');
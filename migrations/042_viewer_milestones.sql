@@ -0,0 +1,43 @@
+-- 042_viewer_milestones.sql
+-- Sub anniversaries, sub streaks and follow anniversaries. The last shared
+-- resub and the follow date of each viewer are kept so milestones crossed
+-- between resub messages, and follow anniversaries, can be found; every
+-- celebrated milestone is stored once per viewer.
+
+CREATE TABLE viewer_sub_history (
+    channel            TEXT NOT NULL,
+    twitch_user_id     TEXT NOT NULL,
+    cumulative_months  INTEGER NOT NULL,
+    streak_months      INTEGER,
+    updated_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (channel, twitch_user_id)
+);
+
+CREATE TABLE viewer_follows (
+    channel         TEXT NOT NULL,
+    twitch_user_id  TEXT NOT NULL,
+    followed_at     TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (channel, twitch_user_id)
+);
+
+CREATE TABLE viewer_milestones (
+    milestone_id    UUID PRIMARY KEY,
+    channel         TEXT NOT NULL,
+    twitch_user_id  TEXT NOT NULL,
+    user_login      TEXT NOT NULL,
+    display_name    TEXT NOT NULL,
+    kind            TEXT NOT NULL
+        CONSTRAINT viewer_milestone_kind
+        CHECK (kind IN ('sub_anniversary', 'sub_streak', 'follow_anniversary')),
+    value           INTEGER NOT NULL,
+    celebrated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE (channel, twitch_user_id, kind, value)
+);
+
+CREATE INDEX idx_viewer_milestones_celebrated ON viewer_milestones(celebrated_at DESC);
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'community', 'twitch.milestone', 'A viewer reached a sub anniversary, sub streak or follow anniversary');
@@ -0,0 +1,45 @@
+-- 043_stream_sessions.sql
+-- One row per broadcast, opened at stream.online and closed at
+-- stream.offline, with running totals so analytics, recaps and leaderboards
+-- can be scoped to "this stream". Markers attach through stream_id; chat and
+-- events through the session's time window. Per-chatter message counts are
+-- kept for per-stream chat leaderboards and the unique chatter count.
+
+CREATE TABLE stream_sessions (
+    session_id       UUID PRIMARY KEY,
+    platform         TEXT NOT NULL DEFAULT 'twitch',
+    channel          TEXT NOT NULL,
+    broadcaster_id   TEXT NOT NULL,
+    stream_id        TEXT NOT NULL UNIQUE,
+    title            TEXT NOT NULL DEFAULT '',
+    category         TEXT NOT NULL DEFAULT '',
+    started_at       TIMESTAMPTZ NOT NULL,
+    ended_at         TIMESTAMPTZ,
+
+    chat_messages    BIGINT NOT NULL DEFAULT 0,
+    unique_chatters  BIGINT NOT NULL DEFAULT 0,
+    follows          BIGINT NOT NULL DEFAULT 0,
+    subs             BIGINT NOT NULL DEFAULT 0,
+    gifted_subs      BIGINT NOT NULL DEFAULT 0,
+    bits             BIGINT NOT NULL DEFAULT 0,
+    raids            BIGINT NOT NULL DEFAULT 0,
+    redemptions      BIGINT NOT NULL DEFAULT 0,
+    hype_moments     BIGINT NOT NULL DEFAULT 0,
+    peak_viewers     BIGINT NOT NULL DEFAULT 0,
+
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_stream_sessions_started ON stream_sessions(started_at DESC);
+CREATE INDEX idx_stream_sessions_open ON stream_sessions(channel) WHERE ended_at IS NULL;
+
+CREATE TABLE stream_session_chatters (
+    session_id      UUID NOT NULL REFERENCES stream_sessions(session_id) ON DELETE CASCADE,
+    twitch_user_id  TEXT NOT NULL,
+    display_name    TEXT NOT NULL,
+    messages        BIGINT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (session_id, twitch_user_id)
+);
+
+CREATE INDEX idx_stream_session_chatters_messages ON stream_session_chatters(session_id, messages DESC);
@@ -0,0 +1,5 @@
+-- 044_crowd_votes.sql
+-- Crowd control votes: the pipeline trigger for a decided vote.
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch', 'community', 'crowd.vote_decided', 'A chat vote among OSC effects closed with a winner');
@@ -0,0 +1,7 @@
+-- 045_command_responses.sql
+-- Custom commands can answer with a fixed text (with {user}, {touser},
+-- {channel} and {args} filled in) instead of built-in logic, e.g. commands
+-- imported from another bot.
+
+ALTER TABLE commands
+    ADD COLUMN response TEXT;
@@ -0,0 +1,39 @@
+-- 046_workspace_channels.sql
+-- Route chat channels to workspaces, so each broadcaster's commands, redeems,
+-- config and credentials answer in their own channels; scope API tokens to a
+-- workspace; and stop a workspace delete from silently taking its stored
+-- credentials with it.
+
+---------------------------------------------------------------------------
+-- CHANNEL ROUTES
+---------------------------------------------------------------------------
+
+-- platform is "twitch" for Twitch chat, Helix and EventSub alike; channel is
+-- lowercase without IRC's '#'. Channels without a route use 'default'.
+CREATE TABLE workspace_channels (
+    platform        TEXT NOT NULL,
+    channel         TEXT NOT NULL,
+    workspace_id    UUID NOT NULL REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (platform, channel)
+);
+
+CREATE INDEX idx_workspace_channels_workspace ON workspace_channels(workspace_id);
+
+---------------------------------------------------------------------------
+-- API TOKENS
+---------------------------------------------------------------------------
+
+-- NULL: the token may use every workspace
+ALTER TABLE api_tokens
+    ADD COLUMN workspace_id UUID REFERENCES workspaces(workspace_id) ON DELETE CASCADE;
+
+---------------------------------------------------------------------------
+-- PLATFORM CREDENTIALS
+---------------------------------------------------------------------------
+
+ALTER TABLE platform_credentials DROP CONSTRAINT platform_credentials_workspace_id_fkey;
+ALTER TABLE platform_credentials
+    ADD CONSTRAINT platform_credentials_workspace_id_fkey
+        FOREIGN KEY (workspace_id) REFERENCES workspaces(workspace_id) ON DELETE RESTRICT;
@@ -0,0 +1,6 @@
+-- 047_drop_stray_updated_at_triggers.sql
+-- users and platform_identities have no updated_at column, so the triggers
+-- 001 put on them failed every UPDATE to either table.
+
+DROP TRIGGER IF EXISTS update_users_updated_at ON users;
+DROP TRIGGER IF EXISTS update_platform_identities_updated_at ON platform_identities;
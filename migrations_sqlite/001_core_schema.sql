@@ -0,0 +1,191 @@
+-- 001_core_schema.sql (SQLite)
+-- Embedded backend: the tables behind workspaces, users, platform identities,
+-- platform config, credentials, bot config, commands, redeems and API tokens.
+-- Mirrors the Postgres schema in ../migrations with SQLite types:
+-- UUIDs are 16-byte BLOBs, timestamps are RFC 3339 TEXT, JSON is TEXT.
+
+---------------------------------------------------------------------------
+-- WORKSPACES
+---------------------------------------------------------------------------
+
+CREATE TABLE workspaces (
+    workspace_id    BLOB PRIMARY KEY,
+    name            TEXT NOT NULL UNIQUE,
+    display_name    TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO workspaces (workspace_id, name, display_name)
+VALUES (X'00000000000000000000000000000000', 'default', 'Default');
+
+---------------------------------------------------------------------------
+-- USERS
+---------------------------------------------------------------------------
+
+CREATE TABLE users (
+    user_id         BLOB PRIMARY KEY,
+    global_username TEXT UNIQUE,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    is_active       INTEGER NOT NULL DEFAULT 1
+);
+
+CREATE INDEX idx_users_global_username_lower ON users(LOWER(global_username));
+
+CREATE TABLE platform_identities (
+    platform_identity_id  BLOB PRIMARY KEY,
+    user_id               BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    platform              TEXT NOT NULL,
+    platform_user_id      TEXT NOT NULL,
+    platform_username     TEXT NOT NULL,
+    platform_display_name TEXT,
+    platform_roles        TEXT NOT NULL DEFAULT '[]',
+    platform_data         TEXT NOT NULL DEFAULT '{}',
+    created_at            TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_updated          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (platform, platform_user_id),
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs'))
+);
+
+CREATE INDEX idx_platform_identities_user ON platform_identities(user_id);
+
+---------------------------------------------------------------------------
+-- PLATFORM CONFIG & CREDENTIALS
+---------------------------------------------------------------------------
+
+CREATE TABLE platform_config (
+    platform_config_id BLOB PRIMARY KEY,
+    platform           TEXT NOT NULL UNIQUE,
+    client_id          TEXT,
+    client_secret      TEXT,
+    created_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs'))
+);
+
+CREATE TABLE platform_credentials (
+    credential_id   BLOB PRIMARY KEY,
+    platform        TEXT NOT NULL,
+    platform_id     TEXT,
+    credential_type TEXT NOT NULL,
+    user_id         BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    user_name       TEXT NOT NULL,
+    primary_token   TEXT NOT NULL, -- encrypted by the application
+    refresh_token   TEXT,          -- encrypted by the application
+    additional_data TEXT,          -- encrypted by the application
+    expires_at      TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    is_broadcaster  INTEGER NOT NULL DEFAULT 0,
+    is_teammate     INTEGER NOT NULL DEFAULT 0,
+    is_bot          INTEGER NOT NULL DEFAULT 0,
+    workspace_id    BLOB NOT NULL DEFAULT X'00000000000000000000000000000000'
+                    REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+
+    UNIQUE (platform, user_id),
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs')),
+    CHECK (credential_type IN ('oauth2', 'apikey', 'bearer', 'jwt', 'vc', 'interactive2fa'))
+);
+
+CREATE INDEX idx_platform_credentials_workspace ON platform_credentials(workspace_id);
+
+---------------------------------------------------------------------------
+-- BOT CONFIG
+---------------------------------------------------------------------------
+
+CREATE TABLE bot_config (
+    workspace_id   BLOB NOT NULL DEFAULT X'00000000000000000000000000000000'
+                   REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    config_key     TEXT NOT NULL,
+    config_value   TEXT NOT NULL,
+    config_meta    TEXT,
+    created_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (workspace_id, config_key)
+);
+
+---------------------------------------------------------------------------
+-- COMMANDS & REDEEMS
+---------------------------------------------------------------------------
+
+CREATE TABLE commands (
+    command_id              BLOB PRIMARY KEY,
+    workspace_id            BLOB NOT NULL DEFAULT X'00000000000000000000000000000000'
+                            REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    platform                TEXT NOT NULL,
+    command_name            TEXT NOT NULL,
+    min_role                TEXT NOT NULL DEFAULT 'viewer',
+    is_active               INTEGER NOT NULL DEFAULT 1,
+    created_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    cooldown_seconds        INTEGER NOT NULL DEFAULT 0,
+    cooldown_warnonce       INTEGER NOT NULL DEFAULT 0,
+    respond_with_credential BLOB REFERENCES platform_credentials(credential_id) ON DELETE SET NULL,
+    active_credential_id    BLOB REFERENCES platform_credentials(credential_id) ON DELETE SET NULL,
+    stream_online_only      INTEGER NOT NULL DEFAULT 0,
+    stream_offline_only     INTEGER NOT NULL DEFAULT 0,
+
+    UNIQUE (workspace_id, platform, command_name),
+    CHECK (min_role IN ('viewer', 'subscriber', 'vip', 'moderator', 'broadcaster'))
+);
+
+CREATE INDEX idx_commands_workspace ON commands(workspace_id);
+
+CREATE TABLE redeems (
+    redeem_id               BLOB PRIMARY KEY,
+    workspace_id            BLOB NOT NULL DEFAULT X'00000000000000000000000000000000'
+                            REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    platform                TEXT NOT NULL,
+    reward_id               TEXT NOT NULL,
+    reward_name             TEXT NOT NULL,
+    cost                    INTEGER NOT NULL DEFAULT 100,
+    is_active               INTEGER NOT NULL DEFAULT 1,
+    dynamic_pricing         INTEGER NOT NULL DEFAULT 0,
+    active_offline          INTEGER NOT NULL DEFAULT 0,
+    is_managed              INTEGER NOT NULL DEFAULT 0,
+    plugin_name             TEXT,
+    command_name            TEXT,
+    created_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    active_credential_id    BLOB REFERENCES platform_credentials(credential_id) ON DELETE SET NULL,
+    is_input_required       INTEGER NOT NULL DEFAULT 0,
+    redeem_prompt_text      TEXT,
+
+    UNIQUE (workspace_id, platform, reward_id),
+    CHECK (platform IN ('twitch', 'discord', 'vrchat'))
+);
+
+CREATE INDEX idx_redeems_workspace ON redeems(workspace_id);
+
+---------------------------------------------------------------------------
+-- API TOKENS & AUDIT LOG
+---------------------------------------------------------------------------
+
+CREATE TABLE api_tokens (
+    token_id        BLOB PRIMARY KEY,
+    name            TEXT NOT NULL UNIQUE,
+    role            TEXT NOT NULL,
+    token_hash      TEXT NOT NULL UNIQUE,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at    TEXT,
+    revoked_at      TEXT,
+
+    CHECK (role IN ('admin', 'moderator', 'readonly'))
+);
+
+CREATE TABLE grpc_audit_log (
+    audit_id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    token_id        BLOB REFERENCES api_tokens(token_id) ON DELETE SET NULL,
+    client_name     TEXT,
+    role            TEXT,
+    method          TEXT NOT NULL,
+    workspace       TEXT,
+    allowed         INTEGER NOT NULL,
+    reason          TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_grpc_audit_log_created ON grpc_audit_log(created_at DESC);
@@ -0,0 +1,81 @@
+-- 002_kick_platform.sql (SQLite)
+-- Allow 'kick' as a platform. SQLite can't alter a CHECK constraint, so the
+-- three tables are rebuilt. Dropping platform_credentials would null the
+-- credential links on commands and redeems (ON DELETE SET NULL), so those
+-- are saved first and restored afterwards.
+
+CREATE TEMP TABLE saved_command_links AS
+    SELECT command_id, respond_with_credential, active_credential_id FROM commands;
+CREATE TEMP TABLE saved_redeem_links AS
+    SELECT redeem_id, active_credential_id FROM redeems;
+
+CREATE TABLE platform_identities_new (
+    platform_identity_id  BLOB PRIMARY KEY,
+    user_id               BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    platform              TEXT NOT NULL,
+    platform_user_id      TEXT NOT NULL,
+    platform_username     TEXT NOT NULL,
+    platform_display_name TEXT,
+    platform_roles        TEXT NOT NULL DEFAULT '[]',
+    platform_data         TEXT NOT NULL DEFAULT '{}',
+    created_at            TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_updated          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (platform, platform_user_id),
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick'))
+);
+INSERT INTO platform_identities_new SELECT * FROM platform_identities;
+DROP TABLE platform_identities;
+ALTER TABLE platform_identities_new RENAME TO platform_identities;
+CREATE INDEX idx_platform_identities_user ON platform_identities(user_id);
+
+CREATE TABLE platform_config_new (
+    platform_config_id BLOB PRIMARY KEY,
+    platform           TEXT NOT NULL UNIQUE,
+    client_id          TEXT,
+    client_secret      TEXT,
+    created_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick'))
+);
+INSERT INTO platform_config_new SELECT * FROM platform_config;
+DROP TABLE platform_config;
+ALTER TABLE platform_config_new RENAME TO platform_config;
+
+CREATE TABLE platform_credentials_new (
+    credential_id   BLOB PRIMARY KEY,
+    platform        TEXT NOT NULL,
+    platform_id     TEXT,
+    credential_type TEXT NOT NULL,
+    user_id         BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    user_name       TEXT NOT NULL,
+    primary_token   TEXT NOT NULL, -- encrypted by the application
+    refresh_token   TEXT,          -- encrypted by the application
+    additional_data TEXT,          -- encrypted by the application
+    expires_at      TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    is_broadcaster  INTEGER NOT NULL DEFAULT 0,
+    is_teammate     INTEGER NOT NULL DEFAULT 0,
+    is_bot          INTEGER NOT NULL DEFAULT 0,
+    workspace_id    BLOB NOT NULL DEFAULT X'00000000000000000000000000000000'
+                    REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+
+    UNIQUE (platform, user_id),
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick')),
+    CHECK (credential_type IN ('oauth2', 'apikey', 'bearer', 'jwt', 'vc', 'interactive2fa'))
+);
+INSERT INTO platform_credentials_new SELECT * FROM platform_credentials;
+DROP TABLE platform_credentials;
+ALTER TABLE platform_credentials_new RENAME TO platform_credentials;
+CREATE INDEX idx_platform_credentials_workspace ON platform_credentials(workspace_id);
+
+UPDATE commands SET
+    respond_with_credential = (SELECT s.respond_with_credential FROM saved_command_links s WHERE s.command_id = commands.command_id),
+    active_credential_id    = (SELECT s.active_credential_id FROM saved_command_links s WHERE s.command_id = commands.command_id);
+UPDATE redeems SET
+    active_credential_id = (SELECT s.active_credential_id FROM saved_redeem_links s WHERE s.redeem_id = redeems.redeem_id);
+
+DROP TABLE saved_command_links;
+DROP TABLE saved_redeem_links;
@@ -0,0 +1,7 @@
+-- 003_command_cooldowns.sql (SQLite)
+-- Per-user and per-role cooldowns and the moderator bypass flag, as in
+-- ../migrations/017_command_cooldowns.sql.
+
+ALTER TABLE commands ADD COLUMN user_cooldown_seconds INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE commands ADD COLUMN role_cooldowns TEXT NOT NULL DEFAULT '{}';
+ALTER TABLE commands ADD COLUMN cooldown_bypass_mods INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- 004_command_reply_privately.sql (SQLite)
+-- Whispered command replies, as in ../migrations/018_command_reply_privately.sql.
+
+ALTER TABLE commands ADD COLUMN reply_privately INTEGER NOT NULL DEFAULT 0;
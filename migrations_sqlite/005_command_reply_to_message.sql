@@ -0,0 +1,4 @@
+-- 005_command_reply_to_message.sql (SQLite)
+-- Threaded command replies, as in ../migrations/025_command_reply_to_message.sql.
+
+ALTER TABLE commands ADD COLUMN reply_to_message INTEGER NOT NULL DEFAULT 0;
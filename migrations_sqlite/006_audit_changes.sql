@@ -0,0 +1,10 @@
+-- 006_audit_changes.sql (SQLite)
+-- Change records in the gRPC audit log, as in ../migrations/028_audit_changes.sql.
+-- before_value and after_value hold JSON text.
+
+ALTER TABLE grpc_audit_log ADD COLUMN target TEXT;
+ALTER TABLE grpc_audit_log ADD COLUMN before_value TEXT;
+ALTER TABLE grpc_audit_log ADD COLUMN after_value TEXT;
+ALTER TABLE grpc_audit_log ADD COLUMN outcome TEXT;
+
+CREATE INDEX idx_grpc_audit_log_method ON grpc_audit_log(method);
@@ -0,0 +1,4 @@
+-- 007_redeem_approval.sql (SQLite)
+-- Redeems that wait for approval, as in ../migrations/039_redeem_approvals.sql.
+
+ALTER TABLE redeems ADD COLUMN requires_approval INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- 008_command_responses.sql (SQLite)
+-- Text responses for custom commands, as in ../migrations/045_command_responses.sql.
+
+ALTER TABLE commands ADD COLUMN response TEXT;
@@ -0,0 +1,5 @@
+-- 009_bot_config_secrets.sql (SQLite)
+-- Flags bot_config rows holding encrypted secrets, as the Postgres schema's
+-- bot_config.is_sensitive does.
+
+ALTER TABLE bot_config ADD COLUMN is_sensitive INTEGER NOT NULL DEFAULT 0;
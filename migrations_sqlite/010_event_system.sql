@@ -0,0 +1,323 @@
+-- 010_event_system.sql (SQLite)
+-- Event pipelines, the event type and handler registries, pipeline throttles
+-- and command/redeem usage history, as in ../migrations/002_event_system.sql,
+-- 026_command_usage_timing.sql and 027_pipeline_throttles.sql. Postgres
+-- partitions the logs by time; here they are plain tables. Arrays (tags) are
+-- JSON text.
+
+---------------------------------------------------------------------------
+-- REGISTRIES
+---------------------------------------------------------------------------
+
+CREATE TABLE event_type_registry (
+    event_type_id   BLOB PRIMARY KEY DEFAULT (randomblob(16)),
+    platform        TEXT NOT NULL,
+    event_category  TEXT NOT NULL,
+    event_name      TEXT NOT NULL,
+    description     TEXT,
+    event_schema    TEXT,
+    is_enabled      INTEGER NOT NULL DEFAULT 1,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (platform, event_name),
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick', 'system'))
+);
+
+CREATE INDEX idx_event_type_platform ON event_type_registry(platform);
+CREATE INDEX idx_event_type_category ON event_type_registry(event_category);
+
+CREATE TABLE event_handler_registry (
+    handler_id       BLOB PRIMARY KEY DEFAULT (randomblob(16)),
+    handler_type     TEXT NOT NULL,
+    handler_name     TEXT NOT NULL UNIQUE,
+    handler_category TEXT NOT NULL,
+    description      TEXT,
+    parameters       TEXT,
+    is_builtin       INTEGER NOT NULL DEFAULT 0,
+    plugin_id        TEXT,
+    is_enabled       INTEGER NOT NULL DEFAULT 1,
+    created_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (handler_type IN ('filter', 'action'))
+);
+
+CREATE INDEX idx_handler_registry_type ON event_handler_registry(handler_type);
+CREATE INDEX idx_handler_registry_category ON event_handler_registry(handler_category);
+
+---------------------------------------------------------------------------
+-- PIPELINES
+---------------------------------------------------------------------------
+
+CREATE TABLE event_pipelines (
+    pipeline_id     BLOB PRIMARY KEY,
+    name            TEXT NOT NULL UNIQUE,
+    description     TEXT,
+    enabled         INTEGER NOT NULL DEFAULT 1,
+    priority        INTEGER NOT NULL DEFAULT 100,
+    stop_on_match   INTEGER NOT NULL DEFAULT 0,
+    stop_on_error   INTEGER NOT NULL DEFAULT 0,
+    created_by      BLOB REFERENCES users(user_id) ON DELETE SET NULL,
+    is_system       INTEGER NOT NULL DEFAULT 0,
+    tags            TEXT NOT NULL DEFAULT '[]',
+    metadata        TEXT NOT NULL DEFAULT '{}',
+    execution_count INTEGER NOT NULL DEFAULT 0,
+    success_count   INTEGER NOT NULL DEFAULT 0,
+    last_executed   TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_pipelines_enabled ON event_pipelines(enabled);
+CREATE INDEX idx_pipelines_priority ON event_pipelines(priority);
+
+CREATE TABLE pipeline_filters (
+    filter_id       BLOB PRIMARY KEY,
+    pipeline_id     BLOB NOT NULL REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    filter_type     TEXT NOT NULL REFERENCES event_handler_registry(handler_name),
+    filter_config   TEXT NOT NULL DEFAULT '{}',
+    filter_order    INTEGER NOT NULL DEFAULT 0,
+    is_negated      INTEGER NOT NULL DEFAULT 0,
+    is_required     INTEGER NOT NULL DEFAULT 1,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_pipeline_filters_pipeline ON pipeline_filters(pipeline_id);
+
+CREATE TABLE pipeline_actions (
+    action_id         BLOB PRIMARY KEY,
+    pipeline_id       BLOB NOT NULL REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    action_type       TEXT NOT NULL REFERENCES event_handler_registry(handler_name),
+    action_config     TEXT NOT NULL DEFAULT '{}',
+    action_order      INTEGER NOT NULL DEFAULT 0,
+    continue_on_error INTEGER NOT NULL DEFAULT 1,
+    is_async          INTEGER NOT NULL DEFAULT 0,
+    timeout_ms        INTEGER,
+    retry_count       INTEGER DEFAULT 0,
+    retry_delay_ms    INTEGER DEFAULT 1000,
+    condition_type    TEXT,
+    condition_config  TEXT,
+    created_at        TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at        TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (condition_type IS NULL OR
+           condition_type IN ('previous_success', 'previous_failure', 'data_check', 'expression'))
+);
+
+CREATE INDEX idx_pipeline_actions_pipeline ON pipeline_actions(pipeline_id);
+
+CREATE TABLE pipeline_execution_log (
+    execution_id      BLOB PRIMARY KEY,
+    pipeline_id       BLOB NOT NULL REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    event_type        TEXT NOT NULL,
+    event_data        TEXT NOT NULL,
+    started_at        TEXT NOT NULL,
+    completed_at      TEXT,
+    duration_ms       INTEGER,
+    status            TEXT NOT NULL DEFAULT 'running',
+    error_message     TEXT,
+    actions_executed  INTEGER DEFAULT 0,
+    actions_succeeded INTEGER DEFAULT 0,
+    action_results    TEXT DEFAULT '[]',
+    triggered_by      BLOB REFERENCES users(user_id) ON DELETE SET NULL,
+    platform          TEXT,
+
+    CHECK (status IN ('running', 'success', 'failed', 'timeout', 'cancelled'))
+);
+
+CREATE INDEX idx_execution_log_recent ON pipeline_execution_log(pipeline_id, started_at DESC);
+CREATE INDEX idx_execution_log_started ON pipeline_execution_log(started_at DESC);
+
+CREATE TABLE pipeline_shared_data (
+    shared_data_id  BLOB PRIMARY KEY,
+    execution_id    BLOB NOT NULL,
+    data_key        TEXT NOT NULL,
+    data_value      TEXT NOT NULL,
+    data_type       TEXT,
+    set_by_action   BLOB REFERENCES pipeline_actions(action_id) ON DELETE SET NULL,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (execution_id, data_key)
+);
+
+CREATE TABLE pipeline_throttles (
+    pipeline_id             BLOB PRIMARY KEY REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    cooldown_seconds        INTEGER NOT NULL DEFAULT 0,
+    user_cooldown_seconds   INTEGER NOT NULL DEFAULT 0,
+    max_executions          INTEGER NOT NULL DEFAULT 0,
+    max_executions_per_user INTEGER NOT NULL DEFAULT 0,
+    window_seconds          INTEGER NOT NULL DEFAULT 60,
+    updated_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (cooldown_seconds >= 0 AND user_cooldown_seconds >= 0 AND
+           max_executions >= 0 AND max_executions_per_user >= 0 AND window_seconds >= 0)
+);
+
+CREATE TABLE pipeline_throttle_hits (
+    pipeline_id BLOB NOT NULL REFERENCES event_pipelines(pipeline_id) ON DELETE CASCADE,
+    scope_key   TEXT NOT NULL,
+    hit_at      TEXT NOT NULL
+);
+
+CREATE INDEX idx_pipeline_throttle_hits_scope ON pipeline_throttle_hits(pipeline_id, scope_key, hit_at DESC);
+
+---------------------------------------------------------------------------
+-- USAGE HISTORY
+---------------------------------------------------------------------------
+
+CREATE TABLE command_usage (
+    usage_id        BLOB PRIMARY KEY,
+    command_id      BLOB NOT NULL REFERENCES commands(command_id) ON DELETE CASCADE,
+    user_id         BLOB REFERENCES users(user_id) ON DELETE SET NULL,
+    platform        TEXT NOT NULL,
+    channel         TEXT,
+    input_text      TEXT,
+    response_sent   INTEGER DEFAULT 1,
+    error_message   TEXT,
+    executed_at     TEXT NOT NULL,
+    duration_ms     INTEGER,
+    metadata        TEXT
+);
+
+CREATE INDEX idx_command_usage_command_time ON command_usage(command_id, executed_at DESC);
+CREATE INDEX idx_command_usage_user ON command_usage(user_id);
+CREATE INDEX idx_command_usage_executed ON command_usage(executed_at DESC);
+
+-- The columns the redeem usage repository reads and writes
+CREATE TABLE redeem_usage (
+    usage_id   BLOB PRIMARY KEY,
+    redeem_id  BLOB NOT NULL REFERENCES redeems(redeem_id) ON DELETE CASCADE,
+    user_id    BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    used_at    TEXT NOT NULL,
+    channel    TEXT,
+    usage_data TEXT
+);
+
+CREATE INDEX idx_redeem_usage_redeem ON redeem_usage(redeem_id, used_at DESC);
+CREATE INDEX idx_redeem_usage_user ON redeem_usage(user_id, used_at DESC);
+
+---------------------------------------------------------------------------
+-- SEEDS
+---------------------------------------------------------------------------
+
+INSERT INTO event_type_registry (platform, event_category, event_name, description) VALUES
+    ('twitch-irc', 'chat', 'message.create', 'Chat message received'),
+    ('twitch-irc', 'chat', 'message.delete', 'Chat message deleted'),
+    ('twitch-irc', 'chat', 'user.timeout', 'User timed out'),
+    ('twitch-irc', 'chat', 'user.ban', 'User banned'),
+    ('twitch-eventsub', 'stream', 'stream.online', 'Stream went online'),
+    ('twitch-eventsub', 'stream', 'stream.offline', 'Stream went offline'),
+    ('twitch-eventsub', 'stream', 'channel.update', 'Channel information updated'),
+    ('twitch-eventsub', 'user', 'channel.follow', 'New follower'),
+    ('twitch-eventsub', 'subscription', 'channel.subscribe', 'New subscription'),
+    ('twitch-eventsub', 'subscription', 'channel.subscription.gift', 'Gifted subscriptions'),
+    ('twitch-eventsub', 'subscription', 'channel.subscription.message', 'Resubscription with message'),
+    ('twitch-eventsub', 'points', 'channel.channel_points_custom_reward_redemption.add', 'Channel points redeemed'),
+    ('twitch-eventsub', 'raid', 'channel.raid', 'Channel raided'),
+    ('twitch-eventsub', 'bits', 'channel.cheer', 'Bits cheered'),
+    ('twitch-eventsub', 'moderation', 'channel.moderator.add', 'Moderator added'),
+    ('twitch-eventsub', 'moderation', 'channel.moderator.remove', 'Moderator removed'),
+    ('discord', 'chat', 'message.create', 'Message sent'),
+    ('discord', 'chat', 'message.update', 'Message edited'),
+    ('discord', 'chat', 'message.delete', 'Message deleted'),
+    ('discord', 'member', 'member.join', 'Member joined server'),
+    ('discord', 'member', 'member.leave', 'Member left server'),
+    ('discord', 'member', 'member.update', 'Member updated (roles, nickname, etc)'),
+    ('discord', 'voice', 'voice.join', 'Member joined voice channel'),
+    ('discord', 'voice', 'voice.leave', 'Member left voice channel'),
+    ('discord', 'voice', 'voice.move', 'Member moved voice channels'),
+    ('discord', 'presence', 'presence.update', 'Member presence updated (status, activity)'),
+    ('discord', 'interaction', 'interaction.create', 'Slash command or button interaction'),
+    ('discord', 'bot', 'ready', 'Bot connected and ready'),
+    ('system', 'lifecycle', 'startup', 'Bot started'),
+    ('system', 'lifecycle', 'shutdown', 'Bot shutting down'),
+    ('system', 'plugin', 'plugin.loaded', 'Plugin loaded'),
+    ('system', 'plugin', 'plugin.unloaded', 'Plugin unloaded'),
+    ('system', 'error', 'error.critical', 'Critical error occurred'),
+    ('kick', 'chat', 'message.create', 'Chat message received'),
+    ('kick', 'channel', 'kick.follow', 'User followed the channel'),
+    ('kick', 'channel', 'kick.subscription', 'User subscribed or resubscribed'),
+    ('kick', 'channel', 'kick.subscription.gift', 'User gifted subscriptions'),
+    ('system', 'donation', 'donation', 'Tip received through Streamlabs or StreamElements'),
+    ('system', 'heart_rate', 'heart_rate', 'Live heart rate (BPM) changed'),
+    ('obs', 'scene', 'obs.scene_changed', 'The program scene of a connected OBS instance changed'),
+    ('vrchat', 'presence', 'vrchat.friend_joined', 'A friend joined the streamer''s VRChat instance'),
+    ('vrchat', 'presence', 'vrchat.friend_left', 'A friend left the streamer''s VRChat instance'),
+    ('vrchat', 'group', 'vrchat.group_join_request', 'Someone asked to join the streamer''s VRChat group'),
+    ('vrchat', 'group', 'vrchat.group_request_approved', 'A VRChat group join request was approved through the bot'),
+    ('vrchat', 'group', 'vrchat.group_request_rejected', 'A VRChat group join request was rejected through the bot'),
+    ('vrchat', 'group', 'vrchat.group_post', 'The bot posted an announcement to the VRChat group'),
+    ('twitch', 'presence', 'twitch.chatters_changed', 'Viewers joined or left the broadcaster''s chat'),
+    ('twitch', 'schedule', 'twitch.schedule_starting_soon', 'A scheduled stream starts soon and the channel isn''t live yet'),
+    ('twitch', 'stream', 'twitch.hype_moment', 'Chat speed, emote spam, bits or subs spiked while live'),
+    ('system', 'chat', 'responder.triggered', 'A chat responder matched a message and passed its chance and cooldowns'),
+    ('twitch', 'channel_points', 'redeem.approval', 'A redemption was queued for approval, approved, denied or expired'),
+    ('system', 'ops', 'ops.alert', 'An operational problem was detected and routed by the alert rules'),
+    ('twitch', 'moderation', 'moderation.escalated', 'The moderation ladder timed out or banned a repeat offender'),
+    ('twitch', 'community', 'twitch.milestone', 'A viewer reached a sub anniversary, sub streak or follow anniversary'),
+    ('twitch', 'community', 'crowd.vote_decided', 'A chat vote among OSC effects closed with a winner');
+
+INSERT INTO event_handler_registry (handler_type, handler_name, handler_category, description, parameters, is_builtin) VALUES
+    ('filter', 'platform_filter', 'platform', 'Filter by platform',
+     '{"platforms": {"type": "array", "items": "string", "required": true}}', 1),
+    ('filter', 'channel_filter', 'channel', 'Filter by channel name',
+     '{"channels": {"type": "array", "items": "string", "required": true}}', 1),
+    ('filter', 'user_role_filter', 'user', 'Filter by user roles',
+     '{"roles": {"type": "array", "items": "string", "required": true}, "match_any": {"type": "boolean", "default": true}}', 1),
+    ('filter', 'user_level_filter', 'user', 'Filter by user level',
+     '{"min_level": {"type": "string", "enum": ["viewer", "subscriber", "vip", "moderator", "broadcaster"], "required": true}}', 1),
+    ('filter', 'message_pattern_filter', 'message', 'Filter by message pattern',
+     '{"patterns": {"type": "array", "items": "string", "required": true}, "match_any": {"type": "boolean", "default": true}}', 1),
+    ('filter', 'message_length_filter', 'message', 'Filter by message length',
+     '{"min_length": {"type": "integer", "default": 0}, "max_length": {"type": "integer", "default": 500}}', 1),
+    ('filter', 'time_window_filter', 'time', 'Filter by time of day',
+     '{"start_hour": {"type": "integer", "min": 0, "max": 23}, "end_hour": {"type": "integer", "min": 0, "max": 23}, "timezone": {"type": "string", "default": "UTC"}}', 1),
+    ('filter', 'cooldown_filter', 'time', 'Rate limit filter',
+     '{"cooldown_seconds": {"type": "integer", "required": true}, "per_user": {"type": "boolean", "default": true}}', 1),
+    ('filter', 'donation_amount_filter', 'donation', 'Filter donations by amount in the base currency',
+     '{"min_amount": {"type": "number", "default": 0}, "max_amount": {"type": "number"}}', 1),
+    ('action', 'log_action', 'system', 'Log event details',
+     '{"level": {"type": "string", "enum": ["error", "warn", "info", "debug", "trace"], "default": "info"}}', 1),
+    ('action', 'discord_message', 'discord', 'Send Discord message',
+     '{"channel_id": {"type": "string", "required": true}, "message": {"type": "string", "required": true}, "embed": {"type": "object"}}', 1),
+    ('action', 'discord_role_add', 'discord', 'Add Discord role',
+     '{"role_id": {"type": "string", "required": true}, "user_id": {"type": "string", "required": true}}', 1),
+    ('action', 'discord_role_remove', 'discord', 'Remove Discord role',
+     '{"role_id": {"type": "string", "required": true}, "user_id": {"type": "string", "required": true}}', 1),
+    ('action', 'twitch_message', 'twitch', 'Send Twitch chat message',
+     '{"message": {"type": "string", "required": true}, "reply_to": {"type": "string"}, "reply_privately": {"type": "boolean", "default": false}}', 1),
+    ('action', 'twitch_timeout', 'twitch', 'Timeout user on Twitch',
+     '{"user_id": {"type": "string", "required": true}, "duration": {"type": "integer", "default": 600}, "reason": {"type": "string"}}', 1),
+    ('action', 'osc_trigger', 'osc', 'Trigger OSC parameter',
+     '{"address": {"type": "string", "required": true}, "value": {"type": "any", "required": true}, "duration_ms": {"type": "integer"}}', 1),
+    ('action', 'obs_scene_change', 'obs', 'Change OBS scene',
+     '{"scene_name": {"type": "string", "required": true}, "instance": {"type": "string", "default": "default"}}', 1),
+    ('action', 'obs_source_toggle', 'obs', 'Toggle OBS source visibility',
+     '{"source_name": {"type": "string", "required": true}, "visible": {"type": "boolean", "required": true}, "scene": {"type": "string"}}', 1),
+    ('action', 'plugin_call', 'plugin', 'Call plugin function',
+     '{"plugin_id": {"type": "string", "required": true}, "function": {"type": "string", "required": true}, "parameters": {"type": "object"}}', 1),
+    ('action', 'ai_respond', 'ai', 'Generate AI response',
+     '{"agent_id": {"type": "string", "required": true}, "prompt_template": {"type": "string"}}', 1),
+    ('action', 'ai_text_response', 'ai', 'Generate text response with personality',
+     '{"personality_id": {"type": "string", "required": true}, "include_memory": {"type": "boolean", "default": true}, "emotion_influence": {"type": "number", "default": 0.5, "min": 0, "max": 1}}', 1),
+    ('action', 'ai_voice_response', 'ai', 'Generate voice response with TTS',
+     '{"personality_id": {"type": "string", "required": true}, "voice_emotion": {"type": "string", "default": "auto"}, "speed": {"type": "number", "default": 1.0, "min": 0.5, "max": 2.0}}', 1),
+    ('action', 'ai_analyze_input', 'ai', 'Analyze input for intent and emotion',
+     '{"analyze_emotion": {"type": "boolean", "default": true}, "analyze_intent": {"type": "boolean", "default": true}, "update_mood": {"type": "boolean", "default": true}}', 1),
+    ('action', 'avatar_set_emotion', 'robotics', 'Set avatar emotional expression',
+     '{"emotion": {"type": "string", "required": true}, "intensity": {"type": "number", "default": 1.0, "min": 0, "max": 1}, "duration_ms": {"type": "integer", "default": 5000}}', 1),
+    ('action', 'avatar_perform_gesture', 'robotics', 'Trigger avatar gesture or animation',
+     '{"gesture": {"type": "string", "required": true}, "loop": {"type": "boolean", "default": false}, "blend_time": {"type": "integer", "default": 500}}', 1),
+    ('action', 'avatar_move_to', 'robotics', 'Move avatar in VRChat world',
+     '{"position": {"type": "array", "items": {"type": "number"}, "minItems": 3, "maxItems": 3}, "look_at": {"type": "array", "items": {"type": "number"}, "minItems": 3, "maxItems": 3}, "speed": {"type": "number", "default": 1.0}}', 1),
+    ('action', 'avatar_interact', 'robotics', 'Interact with VRChat world object',
+     '{"object_id": {"type": "string", "required": true}, "interaction_type": {"type": "string", "enum": ["touch", "grab", "activate"], "required": true}}', 1),
+    ('action', 'ai_remember', 'ai', 'Store information in AI memory',
+     '{"memory_type": {"type": "string", "default": "conversation"}, "importance": {"type": "number", "default": 0.5, "min": 0, "max": 1}, "auto_expire": {"type": "boolean", "default": true}}', 1),
+    ('action', 'ai_recall', 'ai', 'Retrieve relevant memories',
+     '{"context": {"type": "string", "required": true}, "limit": {"type": "integer", "default": 10}, "min_importance": {"type": "number", "default": 0.3, "min": 0, "max": 1}}', 1),
+    ('action', 'ai_update_mood', 'ai', 'Update AI personality mood',
+     '{"mood_delta": {"type": "object", "required": true}, "reason": {"type": "string"}, "decay_rate": {"type": "number", "default": 0.1, "min": 0, "max": 1}}', 1),
+    ('action', 'post_status', 'social', 'Post a status to Bluesky/Mastodon',
+     '{"networks": {"type": "array", "items": {"type": "string", "enum": ["bluesky", "mastodon"]}, "default": ["bluesky", "mastodon"]}, "message_template": {"type": "string", "default": "{user} is live: {title} {link}"}}', 1);
@@ -0,0 +1,201 @@
+-- 011_analytics.sql (SQLite)
+-- Chat history and its retention config, chat sessions, the bot event
+-- journal, daily stats and chat activity, user analysis and moderation
+-- history, and autostart, as in ../migrations/001_core_schema.sql,
+-- 007_chat_archive.sql, 008_user_notes.sql, 029_chat_activity.sql,
+-- 030_bot_score.sql, 032_ui_event_journal.sql and
+-- 041_moderation_escalation.sql. Chat search uses LIKE instead of a
+-- tsvector.
+
+---------------------------------------------------------------------------
+-- CHAT
+---------------------------------------------------------------------------
+
+CREATE TABLE chat_messages (
+    message_id      BLOB PRIMARY KEY,
+    platform        TEXT NOT NULL,
+    channel         TEXT NOT NULL,
+    user_id         BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    message_text    TEXT NOT NULL,
+    timestamp       TEXT NOT NULL,
+    metadata        TEXT
+);
+
+CREATE INDEX idx_chat_messages_timestamp ON chat_messages(timestamp);
+CREATE INDEX idx_chat_messages_channel ON chat_messages(platform, channel, timestamp DESC);
+CREATE INDEX idx_chat_messages_user ON chat_messages(user_id, timestamp DESC);
+
+CREATE TABLE chat_logging_config (
+    config_id               BLOB PRIMARY KEY DEFAULT (randomblob(16)),
+    platform                TEXT NOT NULL,
+    channel                 TEXT NOT NULL,
+    is_enabled              INTEGER NOT NULL DEFAULT 1,
+    retention_days          INTEGER NOT NULL DEFAULT 30,
+    sampling_rate           REAL NOT NULL DEFAULT 1.0,
+    pre_drop_pipeline_id    BLOB REFERENCES event_pipelines(pipeline_id) ON DELETE SET NULL,
+    created_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (platform, channel),
+    CHECK (sampling_rate > 0 AND sampling_rate <= 1),
+    CHECK (retention_days > 0)
+);
+
+CREATE TABLE chat_sessions (
+    session_id               BLOB PRIMARY KEY,
+    platform                 TEXT NOT NULL,
+    channel                  TEXT NOT NULL,
+    user_id                  BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    joined_at                TEXT NOT NULL,
+    left_at                  TEXT,
+    session_duration_seconds INTEGER,
+    message_count            INTEGER NOT NULL DEFAULT 0,
+    created_at               TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_chat_sessions_user ON chat_sessions(user_id);
+CREATE INDEX idx_chat_sessions_active ON chat_sessions(left_at) WHERE left_at IS NULL;
+
+-- Messages per channel per bucket, bumped as messages are stored
+CREATE TABLE chat_activity (
+    platform      TEXT NOT NULL,
+    channel       TEXT NOT NULL,
+    bucket_start  TEXT NOT NULL,
+    message_count INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (platform, channel, bucket_start)
+);
+
+CREATE INDEX idx_chat_activity_bucket ON chat_activity(bucket_start);
+
+---------------------------------------------------------------------------
+-- EVENTS AND STATS
+---------------------------------------------------------------------------
+
+-- seq and kind are set for the events sent to UI clients
+CREATE TABLE bot_events (
+    event_id        BLOB PRIMARY KEY DEFAULT (randomblob(16)),
+    event_type      TEXT NOT NULL,
+    event_timestamp TEXT NOT NULL,
+    data            TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    seq             INTEGER,
+    kind            TEXT
+);
+
+CREATE INDEX idx_bot_events_type ON bot_events(event_type);
+CREATE INDEX idx_bot_events_timestamp ON bot_events(event_timestamp DESC);
+CREATE UNIQUE INDEX idx_bot_events_seq ON bot_events(seq);
+
+CREATE TABLE daily_stats (
+    date                TEXT PRIMARY KEY,
+    total_messages      INTEGER NOT NULL DEFAULT 0,
+    total_chat_visits   INTEGER NOT NULL DEFAULT 0,
+    unique_users        INTEGER NOT NULL DEFAULT 0,
+    created_at          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+---------------------------------------------------------------------------
+-- USER ANALYSIS AND MODERATION HISTORY
+---------------------------------------------------------------------------
+
+CREATE TABLE user_analysis (
+    user_analysis_id      BLOB PRIMARY KEY,
+    user_id               BLOB NOT NULL UNIQUE REFERENCES users(user_id) ON DELETE CASCADE,
+    spam_score            REAL NOT NULL DEFAULT 0.0,
+    intelligibility_score REAL NOT NULL DEFAULT 0.5,
+    quality_score         REAL NOT NULL DEFAULT 0.5,
+    horni_score           REAL NOT NULL DEFAULT 0.0,
+    ai_notes              TEXT,
+    moderator_notes       TEXT,
+    created_at            TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at            TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    bot_score             REAL NOT NULL DEFAULT 0.0,
+    bot_signals           TEXT NOT NULL DEFAULT '[]',
+    bot_scored_at         TEXT,
+
+    CHECK (spam_score >= 0 AND spam_score <= 1),
+    CHECK (intelligibility_score >= 0 AND intelligibility_score <= 1),
+    CHECK (quality_score >= 0 AND quality_score <= 1),
+    CHECK (horni_score >= 0 AND horni_score <= 1)
+);
+
+CREATE INDEX idx_user_analysis_scores ON user_analysis(spam_score, quality_score);
+CREATE INDEX idx_user_analysis_bot_score ON user_analysis(bot_score) WHERE bot_score > 0;
+
+CREATE TABLE user_analysis_history (
+    user_analysis_history_id BLOB PRIMARY KEY,
+    user_id                  BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    year_month               TEXT NOT NULL,
+    spam_score               REAL NOT NULL DEFAULT 0.0,
+    intelligibility_score    REAL NOT NULL DEFAULT 0.5,
+    quality_score            REAL NOT NULL DEFAULT 0.5,
+    horni_score              REAL NOT NULL DEFAULT 0.0,
+    ai_notes                 TEXT,
+    created_at               TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (user_id, year_month)
+);
+
+CREATE INDEX idx_user_analysis_history_month ON user_analysis_history(year_month);
+
+CREATE TABLE user_audit_log (
+    audit_id      BLOB PRIMARY KEY,
+    user_id       BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    action        TEXT NOT NULL,
+    field_name    TEXT,
+    old_value     TEXT,
+    new_value     TEXT,
+    performed_by  BLOB REFERENCES users(user_id) ON DELETE SET NULL,
+    performed_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    metadata      TEXT
+);
+
+CREATE INDEX idx_user_audit_log_user_id ON user_audit_log(user_id);
+CREATE INDEX idx_user_audit_log_performed_at ON user_audit_log(performed_at);
+
+CREATE TABLE user_notes (
+    note_id     BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    author      TEXT NOT NULL,
+    note_text   TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_user_notes_user_id ON user_notes(user_id, created_at DESC);
+
+-- category ties an action to a moderation rule's escalation ladder
+CREATE TABLE user_moderation_actions (
+    action_id         BLOB PRIMARY KEY,
+    user_id           BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    platform          TEXT NOT NULL,
+    channel           TEXT NOT NULL DEFAULT '',
+    action_type       TEXT NOT NULL,
+    reason            TEXT,
+    duration_seconds  INTEGER,
+    moderator         TEXT NOT NULL,
+    created_at        TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    category          TEXT NOT NULL DEFAULT '',
+
+    CHECK (action_type IN ('timeout', 'ban', 'unban', 'warning', 'pardon'))
+);
+
+CREATE INDEX idx_user_moderation_actions_user_id ON user_moderation_actions(user_id, created_at DESC);
+CREATE INDEX idx_user_moderation_actions_category
+    ON user_moderation_actions(user_id, category, created_at DESC)
+    WHERE category <> '';
+
+---------------------------------------------------------------------------
+-- AUTOSTART
+---------------------------------------------------------------------------
+
+CREATE TABLE autostart (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    platform      TEXT NOT NULL,
+    account_name  TEXT NOT NULL,
+    enabled       INTEGER NOT NULL DEFAULT 1,
+    created_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (platform, account_name)
+);
@@ -0,0 +1,434 @@
+-- 012_platform_features.sql (SQLite)
+-- Discord accounts, guilds, channels and event config, the AI tables, OSC
+-- triggers and chat controls, OBS instances and drip outfits, as in
+-- ../migrations/003_platform_features.sql, 004_seed_data.sql and
+-- 038_osc_chat_controls.sql. OSC triggers, OBS instances and drip use the
+-- columns their repositories read (../migrations/old/004, 008, 010 and
+-- 011). Tables no repository reads (personalities, drip credits) are left
+-- out.
+
+---------------------------------------------------------------------------
+-- DISCORD
+---------------------------------------------------------------------------
+
+CREATE TABLE discord_accounts (
+    account_name    TEXT PRIMARY KEY,
+    discord_id      TEXT UNIQUE,
+    credential_id   BLOB REFERENCES platform_credentials(credential_id) ON DELETE SET NULL,
+    is_active       INTEGER NOT NULL DEFAULT 0,
+    bot_token       TEXT,
+    application_id  TEXT,
+    permissions     INTEGER DEFAULT 0,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE discord_guilds (
+    account_name    TEXT NOT NULL REFERENCES discord_accounts(account_name) ON DELETE CASCADE,
+    guild_id        TEXT NOT NULL,
+    guild_name      TEXT NOT NULL,
+    icon_hash       TEXT,
+    owner_id        TEXT,
+    member_count    INTEGER,
+    is_active       INTEGER NOT NULL DEFAULT 0,
+    permissions     INTEGER,
+    joined_at       TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (account_name, guild_id)
+);
+
+CREATE TABLE discord_channels (
+    account_name    TEXT NOT NULL,
+    guild_id        TEXT NOT NULL,
+    channel_id      TEXT NOT NULL,
+    channel_name    TEXT NOT NULL,
+    channel_type    INTEGER NOT NULL DEFAULT 0,
+    position        INTEGER,
+    topic           TEXT,
+    is_active       INTEGER NOT NULL DEFAULT 0,
+    can_send        INTEGER NOT NULL DEFAULT 1,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (account_name, guild_id, channel_id),
+    FOREIGN KEY (account_name, guild_id)
+        REFERENCES discord_guilds (account_name, guild_id) ON DELETE CASCADE
+);
+
+-- ping_roles is a JSON array of role ids
+CREATE TABLE discord_event_config (
+    event_config_id         BLOB PRIMARY KEY,
+    event_name              TEXT NOT NULL,
+    guild_id                TEXT NOT NULL,
+    channel_id              TEXT NOT NULL,
+    respond_with_credential BLOB REFERENCES platform_credentials(credential_id) ON DELETE SET NULL,
+    ping_roles              TEXT,
+    pipeline_id             BLOB REFERENCES event_pipelines(pipeline_id) ON DELETE SET NULL,
+    is_migrated             INTEGER NOT NULL DEFAULT 0,
+    created_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at              TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (event_name, guild_id, channel_id, respond_with_credential)
+);
+
+CREATE INDEX idx_discord_event_name ON discord_event_config(event_name);
+
+CREATE TABLE discord_live_roles (
+    live_role_id    BLOB PRIMARY KEY,
+    guild_id        TEXT NOT NULL UNIQUE,
+    role_id         TEXT NOT NULL,
+    role_name       TEXT,
+    required_game   TEXT,
+    min_viewers     INTEGER DEFAULT 0,
+    pipeline_id     BLOB REFERENCES event_pipelines(pipeline_id) ON DELETE SET NULL,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+---------------------------------------------------------------------------
+-- AI
+---------------------------------------------------------------------------
+
+CREATE TABLE ai_providers (
+    provider_id BLOB PRIMARY KEY,
+    name        TEXT NOT NULL,
+    description TEXT,
+    enabled     INTEGER NOT NULL DEFAULT 1,
+    created_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_ai_providers_name ON ai_providers (LOWER(name));
+
+CREATE TABLE ai_credentials (
+    credential_id   BLOB PRIMARY KEY,
+    provider_id     BLOB NOT NULL REFERENCES ai_providers(provider_id) ON DELETE CASCADE,
+    api_key         TEXT NOT NULL,
+    api_base        TEXT,
+    is_default      INTEGER NOT NULL DEFAULT 0,
+    additional_data TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE ai_models (
+    model_id     BLOB PRIMARY KEY,
+    provider_id  BLOB NOT NULL REFERENCES ai_providers(provider_id) ON DELETE CASCADE,
+    name         TEXT NOT NULL,
+    description  TEXT,
+    is_default   INTEGER NOT NULL DEFAULT 0,
+    capabilities TEXT,
+    created_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_ai_models_name ON ai_models (provider_id, LOWER(name));
+
+CREATE TABLE ai_system_prompts (
+    prompt_id   BLOB PRIMARY KEY,
+    name        TEXT NOT NULL,
+    content     TEXT NOT NULL,
+    description TEXT,
+    is_default  INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at  TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_ai_system_prompts_name ON ai_system_prompts (LOWER(name));
+
+CREATE TABLE ai_agents (
+    agent_id      BLOB PRIMARY KEY,
+    name          TEXT NOT NULL,
+    description   TEXT,
+    model_id      BLOB NOT NULL REFERENCES ai_models(model_id) ON DELETE CASCADE,
+    system_prompt TEXT,
+    capabilities  TEXT,
+    enabled       INTEGER NOT NULL DEFAULT 1,
+    created_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_ai_agents_name ON ai_agents (LOWER(name));
+
+CREATE TABLE ai_actions (
+    action_id      BLOB PRIMARY KEY,
+    agent_id       BLOB NOT NULL REFERENCES ai_agents(agent_id) ON DELETE CASCADE,
+    name           TEXT NOT NULL,
+    description    TEXT,
+    input_schema   TEXT,
+    output_schema  TEXT,
+    handler_type   TEXT NOT NULL,
+    handler_config TEXT,
+    enabled        INTEGER NOT NULL DEFAULT 1,
+    created_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_ai_actions_name ON ai_actions (agent_id, LOWER(name));
+
+CREATE TABLE ai_triggers (
+    trigger_id    BLOB PRIMARY KEY,
+    trigger_type  TEXT NOT NULL,
+    pattern       TEXT NOT NULL,
+    model_id      BLOB REFERENCES ai_models(model_id) ON DELETE CASCADE,
+    agent_id      BLOB REFERENCES ai_agents(agent_id) ON DELETE CASCADE,
+    system_prompt TEXT,
+    platform      TEXT,
+    channel       TEXT,
+    schedule      TEXT,
+    condition     TEXT,
+    enabled       INTEGER NOT NULL DEFAULT 1,
+    created_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (model_id IS NOT NULL OR agent_id IS NOT NULL)
+);
+
+CREATE UNIQUE INDEX idx_ai_triggers_pattern ON ai_triggers (trigger_type, LOWER(pattern));
+
+CREATE TABLE ai_memory (
+    memory_id         BLOB PRIMARY KEY,
+    user_id           BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    platform          TEXT NOT NULL,
+    role              TEXT NOT NULL,
+    content           TEXT NOT NULL,
+    timestamp         TEXT NOT NULL,
+    metadata          TEXT,
+    memory_type       TEXT DEFAULT 'conversation',
+    importance        REAL DEFAULT 0.5,
+    emotional_context TEXT,
+    accessed_at       TEXT,
+    expires_at        TEXT
+);
+
+CREATE INDEX ai_memory_user_timestamp_idx ON ai_memory (user_id, timestamp DESC);
+
+CREATE TABLE ai_configurations (
+    config_id      BLOB PRIMARY KEY,
+    provider       TEXT NOT NULL,
+    api_key        TEXT,
+    api_endpoint   TEXT,
+    model_settings TEXT DEFAULT '{}',
+    is_active      INTEGER DEFAULT 1,
+    created_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO ai_providers (provider_id, name, description) VALUES
+    (randomblob(16), 'OpenAI', 'OpenAI GPT models provider'),
+    (randomblob(16), 'Anthropic', 'Anthropic Claude models provider'),
+    (randomblob(16), 'Local', 'Local AI models (Ollama, etc)');
+
+INSERT INTO ai_models (model_id, provider_id, name, description, is_default, capabilities) VALUES
+    (randomblob(16), (SELECT provider_id FROM ai_providers WHERE name = 'OpenAI'),
+     'gpt-4o', 'GPT-4o model with strong reasoning capabilities', 1,
+     '{"function_calling": true, "vision": true, "streaming": true, "max_tokens": 128000}'),
+    (randomblob(16), (SELECT provider_id FROM ai_providers WHERE name = 'OpenAI'),
+     'gpt-4o-mini', 'Smaller, faster GPT-4o variant', 0,
+     '{"function_calling": true, "vision": true, "streaming": true, "max_tokens": 128000}'),
+    (randomblob(16), (SELECT provider_id FROM ai_providers WHERE name = 'OpenAI'),
+     'gpt-3.5-turbo', 'Fast and efficient model for simple tasks', 0,
+     '{"function_calling": true, "vision": false, "streaming": true, "max_tokens": 16385}'),
+    (randomblob(16), (SELECT provider_id FROM ai_providers WHERE name = 'Anthropic'),
+     'claude-3-5-sonnet-20241022', 'Most capable Claude model', 1,
+     '{"function_calling": true, "vision": true, "streaming": true, "max_tokens": 200000}'),
+    (randomblob(16), (SELECT provider_id FROM ai_providers WHERE name = 'Anthropic'),
+     'claude-3-5-haiku-20241022', 'Fast Claude model for simple tasks', 0,
+     '{"function_calling": true, "vision": true, "streaming": true, "max_tokens": 200000}');
+
+INSERT INTO ai_system_prompts (prompt_id, name, content, description, is_default) VALUES
+    (randomblob(16), 'Default Assistant',
+     'You are Maow, a helpful AI assistant for a Twitch streamer. Respond to user queries in a friendly, helpful manner. Keep responses concise but informative.',
+     'Default system prompt for general interactions', 1),
+    (randomblob(16), 'Twitch Chat Helper',
+     'You are Maow, a helpful AI assistant for Twitch chat. Keep responses friendly, engaging, and brief (under 200 characters when possible). Avoid controversial topics.',
+     'Optimized for Twitch chat responses', 0),
+    (randomblob(16), 'Gaming Buddy',
+     'You are a knowledgeable gaming companion. Provide tips, celebrate achievements, and engage in gaming discussions. Stay positive and encouraging!',
+     'Gaming-focused assistant', 0),
+    (randomblob(16), 'Creative Helper',
+     'You are a creative assistant helping with art, music, and creative projects. Be encouraging and offer constructive feedback.',
+     'Creative projects assistant', 0);
+
+INSERT INTO ai_agents (agent_id, name, description, model_id, system_prompt, capabilities) VALUES
+    (randomblob(16), 'Maow Assistant',
+     'Default assistant for handling general chat queries',
+     (SELECT model_id FROM ai_models WHERE name = 'claude-3-5-sonnet-20241022'),
+     'You are Maow, a helpful AI assistant that responds to user queries in a friendly manner.',
+     '{"can_search": true, "can_remember": true, "max_response_tokens": 1000}');
+
+INSERT INTO ai_triggers (trigger_id, trigger_type, pattern, model_id, system_prompt) VALUES
+    (randomblob(16), 'prefix', 'hey maow',
+     (SELECT model_id FROM ai_models WHERE name = 'claude-3-5-sonnet-20241022'),
+     'You are Maow, a helpful AI assistant for a Twitch streamer. Respond to user queries in a friendly, helpful manner. Keep responses concise but informative.'),
+    (randomblob(16), 'prefix', '@maow',
+     (SELECT model_id FROM ai_models WHERE name = 'claude-3-5-sonnet-20241022'),
+     'You are Maow, a helpful AI assistant for a Twitch streamer. Respond to user queries in a friendly, helpful manner. Keep responses concise but informative.'),
+    (randomblob(16), 'regex', 'maow (help|info)',
+     (SELECT model_id FROM ai_models WHERE name = 'claude-3-5-sonnet-20241022'),
+     'You are Maow, a helpful AI assistant. Provide clear, helpful information about available features and commands.');
+
+---------------------------------------------------------------------------
+-- OSC
+---------------------------------------------------------------------------
+
+CREATE TABLE osc_triggers (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    redeem_id        BLOB NOT NULL REFERENCES redeems(redeem_id) ON DELETE CASCADE,
+    parameter_name   TEXT NOT NULL,
+    parameter_type   TEXT NOT NULL CHECK (parameter_type IN ('bool', 'int', 'float')),
+    on_value         TEXT NOT NULL,
+    off_value        TEXT NOT NULL,
+    duration_seconds INTEGER,
+    cooldown_seconds INTEGER DEFAULT 0,
+    enabled          INTEGER DEFAULT 1,
+    created_at       TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at       TEXT DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (redeem_id, parameter_name)
+);
+
+CREATE TABLE osc_toggle_states (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    trigger_id   INTEGER NOT NULL REFERENCES osc_triggers(id) ON DELETE CASCADE,
+    user_id      BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    avatar_id    TEXT,
+    activated_at TEXT,
+    expires_at   TEXT,
+    is_active    INTEGER DEFAULT 1,
+
+    UNIQUE (trigger_id, user_id, is_active)
+);
+
+CREATE TABLE osc_avatar_configs (
+    id                 INTEGER PRIMARY KEY AUTOINCREMENT,
+    avatar_id          TEXT NOT NULL UNIQUE,
+    avatar_name        TEXT,
+    parameter_mappings TEXT,
+    created_at         TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE osc_chat_controls (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    name             TEXT NOT NULL UNIQUE,
+    parameter_name   TEXT NOT NULL,
+    parameter_type   TEXT NOT NULL,
+    min_role         TEXT NOT NULL DEFAULT 'moderator',
+    min_value        REAL,
+    max_value        REAL,
+    cooldown_seconds INTEGER NOT NULL DEFAULT 0,
+    enabled          INTEGER NOT NULL DEFAULT 1,
+    created_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (parameter_type IN ('bool', 'int', 'float')),
+    CHECK (min_role IN ('everyone', 'subscriber', 'vip', 'moderator', 'broadcaster')),
+    CHECK (cooldown_seconds >= 0)
+);
+
+---------------------------------------------------------------------------
+-- OBS
+---------------------------------------------------------------------------
+
+CREATE TABLE obs_instances (
+    instance_id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    instance_number    INTEGER NOT NULL UNIQUE CHECK (instance_number > 0),
+    host               TEXT NOT NULL,
+    port               INTEGER NOT NULL DEFAULT 4455 CHECK (port > 0 AND port <= 65535),
+    use_ssl            INTEGER NOT NULL DEFAULT 0,
+    password_encrypted TEXT,
+    use_password       INTEGER NOT NULL DEFAULT 1,
+    is_connected       INTEGER NOT NULL DEFAULT 0,
+    last_connected_at  TEXT,
+    created_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO obs_instances (instance_number, host, port, use_ssl, password_encrypted) VALUES
+    (1, '127.0.0.1', 4455, 0, NULL),
+    (2, '127.0.0.1', 4455, 0, NULL);
+
+---------------------------------------------------------------------------
+-- DRIP
+---------------------------------------------------------------------------
+
+CREATE TABLE drip_avatars (
+    drip_avatar_id      BLOB PRIMARY KEY,
+    user_id             BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    vrchat_avatar_id    TEXT NOT NULL,
+    vrchat_avatar_name  TEXT NOT NULL,
+    local_name          TEXT,
+    created_at          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_avatar_prefix_rules (
+    drip_avatar_prefix_rule_id BLOB PRIMARY KEY,
+    drip_avatar_id             BLOB NOT NULL REFERENCES drip_avatars(drip_avatar_id) ON DELETE CASCADE,
+    rule_type                  TEXT NOT NULL CHECK (rule_type IN ('ignore', 'strip')),
+    prefix                     TEXT NOT NULL,
+    created_at                 TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at                 TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_fits (
+    drip_fit_id    BLOB PRIMARY KEY,
+    drip_avatar_id BLOB NOT NULL REFERENCES drip_avatars(drip_avatar_id) ON DELETE CASCADE,
+    fit_name       TEXT NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_fit_params (
+    drip_fit_param_id BLOB PRIMARY KEY,
+    drip_fit_id       BLOB NOT NULL REFERENCES drip_fits(drip_fit_id) ON DELETE CASCADE,
+    param_name        TEXT NOT NULL,
+    param_value       TEXT NOT NULL,
+    created_at        TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at        TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_props (
+    drip_prop_id BLOB PRIMARY KEY,
+    prop_name    TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_prop_params (
+    drip_prop_param_id BLOB PRIMARY KEY,
+    drip_prop_id       BLOB NOT NULL REFERENCES drip_props(drip_prop_id) ON DELETE CASCADE,
+    param_name         TEXT NOT NULL,
+    param_value        TEXT NOT NULL,
+    created_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_prop_timers (
+    drip_prop_timer_id BLOB PRIMARY KEY,
+    drip_prop_id       BLOB NOT NULL REFERENCES drip_props(drip_prop_id) ON DELETE CASCADE,
+    timer_data         TEXT NOT NULL,
+    created_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_avatar_props (
+    drip_avatar_prop_id BLOB PRIMARY KEY,
+    drip_avatar_id      BLOB NOT NULL REFERENCES drip_avatars(drip_avatar_id) ON DELETE CASCADE,
+    drip_prop_id        BLOB NOT NULL REFERENCES drip_props(drip_prop_id) ON DELETE CASCADE,
+    override_params     TEXT,
+    created_at          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at          TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE drip_avatar_params (
+    drip_avatar_param_id BLOB PRIMARY KEY,
+    drip_avatar_id       BLOB NOT NULL REFERENCES drip_avatars(drip_avatar_id) ON DELETE CASCADE,
+    param_name           TEXT NOT NULL,
+    param_type           TEXT NOT NULL,
+    discovered_at        TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at           TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,403 @@
+-- 013_stream_features.sql (SQLite)
+-- Donations, stream markers and sessions, giveaways, raid protection,
+-- moderation rules, emote stats, posted clips, localization, redeem
+-- schedules and approvals, analysis recompute runs, MIDI mappings,
+-- watchtime, responders, alert rules and viewer milestones, as in
+-- ../migrations/011 through 043. Arrays (responses, kinds) are JSON text.
+
+---------------------------------------------------------------------------
+-- DONATIONS, MARKERS AND SESSIONS
+---------------------------------------------------------------------------
+
+CREATE TABLE donations (
+    donation_id    BLOB PRIMARY KEY,
+    source         TEXT NOT NULL,
+    external_id    TEXT,
+    donor_name     TEXT NOT NULL,
+    message        TEXT,
+    amount         REAL NOT NULL,
+    currency       TEXT NOT NULL,
+    amount_base    REAL,
+    base_currency  TEXT NOT NULL,
+    received_at    TEXT NOT NULL,
+
+    UNIQUE (source, external_id),
+    CHECK (source IN ('streamlabs', 'streamelements'))
+);
+
+CREATE INDEX idx_donations_received_at ON donations(received_at DESC);
+CREATE INDEX idx_donations_donor ON donations(LOWER(donor_name));
+
+CREATE TABLE stream_markers (
+    marker_id         BLOB PRIMARY KEY,
+    broadcaster_id    TEXT NOT NULL,
+    stream_id         TEXT NOT NULL,
+    twitch_marker_id  TEXT,
+    position_seconds  INTEGER NOT NULL,
+    description       TEXT NOT NULL,
+    source            TEXT NOT NULL,
+    created_at        TEXT NOT NULL,
+
+    CHECK (source IN ('raid', 'redeem', 'command', 'scene', 'manual', 'hype'))
+);
+
+CREATE INDEX idx_stream_markers_stream ON stream_markers(stream_id, position_seconds);
+
+CREATE TABLE vod_chapters (
+    stream_id       TEXT PRIMARY KEY,
+    broadcaster_id  TEXT NOT NULL,
+    video_id        TEXT,
+    video_url       TEXT,
+    title           TEXT NOT NULL,
+    chapters        TEXT NOT NULL,
+    marker_count    INTEGER NOT NULL DEFAULT 0,
+    exported_at     TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE stream_sessions (
+    session_id       BLOB PRIMARY KEY,
+    platform         TEXT NOT NULL DEFAULT 'twitch',
+    channel          TEXT NOT NULL,
+    broadcaster_id   TEXT NOT NULL,
+    stream_id        TEXT NOT NULL UNIQUE,
+    title            TEXT NOT NULL DEFAULT '',
+    category         TEXT NOT NULL DEFAULT '',
+    started_at       TEXT NOT NULL,
+    ended_at         TEXT,
+    chat_messages    INTEGER NOT NULL DEFAULT 0,
+    unique_chatters  INTEGER NOT NULL DEFAULT 0,
+    follows          INTEGER NOT NULL DEFAULT 0,
+    subs             INTEGER NOT NULL DEFAULT 0,
+    gifted_subs      INTEGER NOT NULL DEFAULT 0,
+    bits             INTEGER NOT NULL DEFAULT 0,
+    raids            INTEGER NOT NULL DEFAULT 0,
+    redemptions      INTEGER NOT NULL DEFAULT 0,
+    hype_moments     INTEGER NOT NULL DEFAULT 0,
+    peak_viewers     INTEGER NOT NULL DEFAULT 0,
+    updated_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_stream_sessions_started ON stream_sessions(started_at DESC);
+CREATE INDEX idx_stream_sessions_open ON stream_sessions(channel) WHERE ended_at IS NULL;
+
+CREATE TABLE stream_session_chatters (
+    session_id      BLOB NOT NULL REFERENCES stream_sessions(session_id) ON DELETE CASCADE,
+    twitch_user_id  TEXT NOT NULL,
+    display_name    TEXT NOT NULL,
+    messages        INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (session_id, twitch_user_id)
+);
+
+---------------------------------------------------------------------------
+-- GIVEAWAYS, PROTECTION AND MODERATION
+---------------------------------------------------------------------------
+
+CREATE TABLE giveaways (
+    giveaway_id           BLOB PRIMARY KEY,
+    title                 TEXT NOT NULL,
+    channel               TEXT NOT NULL,
+    keyword               TEXT,
+    points_cost           INTEGER NOT NULL DEFAULT 0,
+    reward_id             TEXT,
+    sub_only              INTEGER NOT NULL DEFAULT 0,
+    min_follow_days       INTEGER NOT NULL DEFAULT 0,
+    exclude_winners_days  INTEGER NOT NULL DEFAULT 0,
+    sub_weight            INTEGER NOT NULL DEFAULT 1,
+    status                TEXT NOT NULL DEFAULT 'open',
+    created_at            TEXT NOT NULL,
+    closed_at             TEXT,
+
+    CHECK (status IN ('open', 'closed', 'cancelled')),
+    CHECK (keyword IS NOT NULL OR points_cost > 0),
+    CHECK (sub_weight >= 1)
+);
+
+CREATE INDEX idx_giveaways_status ON giveaways(status, created_at DESC);
+
+CREATE TABLE giveaway_entries (
+    entry_id       BLOB PRIMARY KEY,
+    giveaway_id    BLOB NOT NULL REFERENCES giveaways(giveaway_id) ON DELETE CASCADE,
+    user_id        BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    username       TEXT NOT NULL,
+    is_subscriber  INTEGER NOT NULL DEFAULT 0,
+    weight         INTEGER NOT NULL DEFAULT 1,
+    is_winner      INTEGER NOT NULL DEFAULT 0,
+    rerolled       INTEGER NOT NULL DEFAULT 0,
+    entered_at     TEXT NOT NULL,
+    won_at         TEXT,
+
+    UNIQUE (giveaway_id, user_id)
+);
+
+CREATE INDEX idx_giveaway_entries_winners ON giveaway_entries(user_id, won_at) WHERE is_winner;
+
+CREATE TABLE protection_incidents (
+    incident_id  BLOB PRIMARY KEY,
+    channel      TEXT NOT NULL,
+    trigger      TEXT NOT NULL,
+    details      TEXT NOT NULL DEFAULT '',
+    actions      TEXT NOT NULL DEFAULT '[]',
+    messages     TEXT NOT NULL DEFAULT '[]',
+    started_at   TEXT NOT NULL,
+    ended_at     TEXT,
+    ended_by     TEXT,
+
+    CHECK (trigger IN ('message_rate', 'identical_messages', 'new_accounts', 'manual'))
+);
+
+CREATE INDEX idx_protection_incidents_started ON protection_incidents(started_at DESC);
+
+-- category names the escalation ladder a rule's hits climb
+CREATE TABLE moderation_rules (
+    rule_id       BLOB PRIMARY KEY,
+    name          TEXT NOT NULL UNIQUE,
+    kind          TEXT NOT NULL,
+    pattern       TEXT NOT NULL DEFAULT '',
+    severity      TEXT NOT NULL DEFAULT 'delete',
+    timeout_secs  INTEGER NOT NULL DEFAULT 600,
+    permit_subs   INTEGER NOT NULL DEFAULT 0,
+    permit_vips   INTEGER NOT NULL DEFAULT 0,
+    permit_mods   INTEGER NOT NULL DEFAULT 1,
+    enabled       INTEGER NOT NULL DEFAULT 1,
+    hit_count     INTEGER NOT NULL DEFAULT 0,
+    last_hit_at   TEXT,
+    created_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    category      TEXT NOT NULL DEFAULT '',
+
+    CHECK (kind IN ('link', 'phrase', 'regex')),
+    CHECK (severity IN ('delete', 'timeout', 'ban')),
+    CHECK (timeout_secs BETWEEN 1 AND 1209600)
+);
+
+---------------------------------------------------------------------------
+-- EMOTES, CLIPS AND LOCALIZATION
+---------------------------------------------------------------------------
+
+CREATE TABLE emote_usage_daily (
+    channel     TEXT NOT NULL,
+    day         TEXT NOT NULL,
+    provider    TEXT NOT NULL,
+    emote_id    TEXT NOT NULL,
+    emote_name  TEXT NOT NULL,
+    uses        INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (channel, day, provider, emote_id),
+    CHECK (provider IN ('twitch', '7tv', 'bttv', 'ffz'))
+);
+
+CREATE INDEX idx_emote_usage_daily_channel_day ON emote_usage_daily(channel, day);
+
+CREATE TABLE posted_clips (
+    clip_id         TEXT PRIMARY KEY,
+    broadcaster_id  TEXT NOT NULL,
+    title           TEXT NOT NULL DEFAULT '',
+    creator_name    TEXT NOT NULL DEFAULT '',
+    url             TEXT NOT NULL,
+    created_at      TEXT NOT NULL,
+    posted_at       TEXT NOT NULL,
+    requested_by    TEXT
+);
+
+CREATE INDEX idx_posted_clips_posted_at ON posted_clips(posted_at DESC);
+
+CREATE TABLE language_strings (
+    language     TEXT NOT NULL,
+    message_key  TEXT NOT NULL,
+    template     TEXT NOT NULL,
+    updated_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (language, message_key)
+);
+
+CREATE TABLE channel_languages (
+    platform  TEXT NOT NULL,
+    channel   TEXT NOT NULL,
+    language  TEXT NOT NULL,
+
+    PRIMARY KEY (platform, channel)
+);
+
+---------------------------------------------------------------------------
+-- REDEEM SCHEDULES AND APPROVALS
+---------------------------------------------------------------------------
+
+CREATE TABLE redeem_schedules (
+    schedule_id      BLOB PRIMARY KEY,
+    redeem_id        BLOB NOT NULL REFERENCES redeems(redeem_id) ON DELETE CASCADE,
+    name             TEXT NOT NULL,
+    condition_kind   TEXT NOT NULL,
+    condition_value  TEXT NOT NULL DEFAULT '',
+    action           TEXT NOT NULL,
+    amount           REAL,
+    priority         INTEGER NOT NULL DEFAULT 0,
+    enabled          INTEGER NOT NULL DEFAULT 1,
+    created_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at       TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (redeem_id, name),
+    CHECK (condition_kind IN ('time', 'category', 'hype_train')),
+    CHECK (action IN ('enable', 'disable', 'set_cost', 'multiply_cost'))
+);
+
+CREATE INDEX idx_redeem_schedules_redeem ON redeem_schedules(redeem_id);
+
+CREATE TABLE redeem_approvals (
+    approval_id  BLOB PRIMARY KEY,
+    redeem_id    BLOB NOT NULL REFERENCES redeems(redeem_id) ON DELETE CASCADE,
+    reward_name  TEXT NOT NULL,
+    platform     TEXT NOT NULL,
+    channel      TEXT NOT NULL,
+    user_id      BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    user_name    TEXT NOT NULL,
+    user_input   TEXT NOT NULL DEFAULT '',
+    redemption   TEXT NOT NULL,
+    status       TEXT NOT NULL DEFAULT 'pending',
+    requested_at TEXT NOT NULL,
+    decided_at   TEXT,
+    decided_by   TEXT,
+
+    CHECK (status IN ('pending', 'approved', 'denied', 'expired'))
+);
+
+CREATE INDEX idx_redeem_approvals_pending ON redeem_approvals (requested_at) WHERE status = 'pending';
+
+---------------------------------------------------------------------------
+-- ANALYSIS RECOMPUTE AND MIDI
+---------------------------------------------------------------------------
+
+CREATE TABLE analysis_recompute_runs (
+    run_id          BLOB PRIMARY KEY,
+    since           TEXT NOT NULL,
+    until           TEXT NOT NULL,
+    status          TEXT NOT NULL,
+    total_users     INTEGER NOT NULL DEFAULT 0,
+    processed_users INTEGER NOT NULL DEFAULT 0,
+    last_user_id    BLOB,
+    error           TEXT,
+    started_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL,
+    finished_at     TEXT
+);
+
+CREATE INDEX idx_analysis_recompute_runs_started ON analysis_recompute_runs(started_at DESC);
+
+CREATE TABLE midi_mappings (
+    mapping_id    BLOB PRIMARY KEY,
+    name          TEXT NOT NULL UNIQUE,
+    trigger_kind  TEXT NOT NULL,
+    channel       INTEGER NOT NULL,
+    number        INTEGER NOT NULL,
+    action        TEXT NOT NULL,
+    target        TEXT NOT NULL,
+    argument      TEXT NOT NULL DEFAULT '',
+    enabled       INTEGER NOT NULL DEFAULT 1,
+    created_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (trigger_kind, channel, number),
+    CHECK (trigger_kind IN ('note', 'cc')),
+    CHECK (channel BETWEEN 1 AND 16),
+    CHECK (number BETWEEN 0 AND 127),
+    CHECK (action IN ('osc', 'obs_scene', 'chat'))
+);
+
+---------------------------------------------------------------------------
+-- VIEWERS
+---------------------------------------------------------------------------
+
+CREATE TABLE viewer_watchtime (
+    channel         TEXT NOT NULL,
+    twitch_user_id  TEXT NOT NULL,
+    user_login      TEXT NOT NULL,
+    display_name    TEXT NOT NULL,
+    watch_seconds   INTEGER NOT NULL DEFAULT 0,
+    first_seen      TEXT NOT NULL,
+    last_seen       TEXT NOT NULL,
+
+    PRIMARY KEY (channel, twitch_user_id)
+);
+
+CREATE INDEX idx_viewer_watchtime_login ON viewer_watchtime(channel, user_login);
+
+CREATE TABLE viewer_sub_history (
+    channel            TEXT NOT NULL,
+    twitch_user_id     TEXT NOT NULL,
+    cumulative_months  INTEGER NOT NULL,
+    streak_months      INTEGER,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (channel, twitch_user_id)
+);
+
+CREATE TABLE viewer_follows (
+    channel         TEXT NOT NULL,
+    twitch_user_id  TEXT NOT NULL,
+    followed_at     TEXT NOT NULL,
+
+    PRIMARY KEY (channel, twitch_user_id)
+);
+
+CREATE TABLE viewer_milestones (
+    milestone_id    BLOB PRIMARY KEY,
+    channel         TEXT NOT NULL,
+    twitch_user_id  TEXT NOT NULL,
+    user_login      TEXT NOT NULL,
+    display_name    TEXT NOT NULL,
+    kind            TEXT NOT NULL
+        CHECK (kind IN ('sub_anniversary', 'sub_streak', 'follow_anniversary')),
+    value           INTEGER NOT NULL,
+    celebrated_at   TEXT NOT NULL,
+
+    UNIQUE (channel, twitch_user_id, kind, value)
+);
+
+CREATE INDEX idx_viewer_milestones_celebrated ON viewer_milestones(celebrated_at DESC);
+
+---------------------------------------------------------------------------
+-- RESPONDERS AND ALERTS
+---------------------------------------------------------------------------
+
+CREATE TABLE responders (
+    responder_id       BLOB PRIMARY KEY,
+    name               TEXT NOT NULL UNIQUE,
+    match_kind         TEXT NOT NULL,
+    pattern            TEXT NOT NULL,
+    responses          TEXT NOT NULL DEFAULT '[]',
+    platform           TEXT,
+    channel            TEXT,
+    chance_percent     INTEGER NOT NULL DEFAULT 100,
+    cooldown_secs      INTEGER NOT NULL DEFAULT 30,
+    user_cooldown_secs INTEGER NOT NULL DEFAULT 0,
+    enabled            INTEGER NOT NULL DEFAULT 1,
+    hit_count          INTEGER NOT NULL DEFAULT 0,
+    last_hit_at        TEXT,
+    created_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at         TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (match_kind IN ('regex', 'any', 'all')),
+    CHECK (chance_percent BETWEEN 1 AND 100),
+    CHECK (cooldown_secs >= 0 AND user_cooldown_secs >= 0)
+);
+
+CREATE TABLE alert_rules (
+    rule_id      BLOB PRIMARY KEY,
+    name         TEXT NOT NULL UNIQUE,
+    kinds        TEXT NOT NULL DEFAULT '[]',
+    min_severity TEXT NOT NULL DEFAULT 'warning',
+    route        TEXT NOT NULL,
+    target       TEXT,
+    dedupe_secs  INTEGER NOT NULL DEFAULT 900,
+    quiet_hours  TEXT,
+    enabled      INTEGER NOT NULL DEFAULT 1,
+    created_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at   TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CHECK (min_severity IN ('info', 'warning', 'critical')),
+    CHECK (route IN ('discord_dm', 'chat', 'desktop')),
+    CHECK (dedupe_secs >= 0)
+);
+
+INSERT INTO alert_rules (rule_id, name, min_severity, route, dedupe_secs) VALUES
+    (randomblob(16), 'desktop', 'warning', 'desktop', 900);
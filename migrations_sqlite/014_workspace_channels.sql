@@ -0,0 +1,84 @@
+-- 014_workspace_channels.sql (SQLite)
+-- Channel routes to workspaces, workspace-scoped API tokens, and RESTRICT on
+-- platform_credentials.workspace_id, as in ../migrations/046_workspace_channels.sql.
+-- SQLite can't alter a foreign key, so platform_credentials is rebuilt; the
+-- credential links dropping it would null are saved and restored, as in
+-- 002_kick_platform.sql.
+
+---------------------------------------------------------------------------
+-- CHANNEL ROUTES
+---------------------------------------------------------------------------
+
+CREATE TABLE workspace_channels (
+    platform        TEXT NOT NULL,
+    channel         TEXT NOT NULL,
+    workspace_id    BLOB NOT NULL REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (platform, channel)
+);
+
+CREATE INDEX idx_workspace_channels_workspace ON workspace_channels(workspace_id);
+
+---------------------------------------------------------------------------
+-- API TOKENS
+---------------------------------------------------------------------------
+
+ALTER TABLE api_tokens
+    ADD COLUMN workspace_id BLOB REFERENCES workspaces(workspace_id) ON DELETE CASCADE;
+
+---------------------------------------------------------------------------
+-- PLATFORM CREDENTIALS
+---------------------------------------------------------------------------
+
+CREATE TEMP TABLE saved_command_links AS
+    SELECT command_id, respond_with_credential, active_credential_id FROM commands;
+CREATE TEMP TABLE saved_redeem_links AS
+    SELECT redeem_id, active_credential_id FROM redeems;
+CREATE TEMP TABLE saved_discord_account_links AS
+    SELECT account_name, credential_id FROM discord_accounts;
+CREATE TEMP TABLE saved_discord_event_links AS
+    SELECT event_config_id, respond_with_credential FROM discord_event_config;
+
+CREATE TABLE platform_credentials_new (
+    credential_id   BLOB PRIMARY KEY,
+    platform        TEXT NOT NULL,
+    platform_id     TEXT,
+    credential_type TEXT NOT NULL,
+    user_id         BLOB NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    user_name       TEXT NOT NULL,
+    primary_token   TEXT NOT NULL, -- encrypted by the application
+    refresh_token   TEXT,          -- encrypted by the application
+    additional_data TEXT,          -- encrypted by the application
+    expires_at      TEXT,
+    created_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at      TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    is_broadcaster  INTEGER NOT NULL DEFAULT 0,
+    is_teammate     INTEGER NOT NULL DEFAULT 0,
+    is_bot          INTEGER NOT NULL DEFAULT 0,
+    workspace_id    BLOB NOT NULL DEFAULT X'00000000000000000000000000000000'
+                    REFERENCES workspaces(workspace_id) ON DELETE RESTRICT,
+
+    UNIQUE (platform, user_id),
+    CHECK (platform IN ('twitch', 'twitch-irc', 'twitch-eventsub', 'discord', 'vrchat', 'obs', 'kick')),
+    CHECK (credential_type IN ('oauth2', 'apikey', 'bearer', 'jwt', 'vc', 'interactive2fa'))
+);
+INSERT INTO platform_credentials_new SELECT * FROM platform_credentials;
+DROP TABLE platform_credentials;
+ALTER TABLE platform_credentials_new RENAME TO platform_credentials;
+CREATE INDEX idx_platform_credentials_workspace ON platform_credentials(workspace_id);
+
+UPDATE commands SET
+    respond_with_credential = (SELECT s.respond_with_credential FROM saved_command_links s WHERE s.command_id = commands.command_id),
+    active_credential_id    = (SELECT s.active_credential_id FROM saved_command_links s WHERE s.command_id = commands.command_id);
+UPDATE redeems SET
+    active_credential_id = (SELECT s.active_credential_id FROM saved_redeem_links s WHERE s.redeem_id = redeems.redeem_id);
+UPDATE discord_accounts SET
+    credential_id = (SELECT s.credential_id FROM saved_discord_account_links s WHERE s.account_name = discord_accounts.account_name);
+UPDATE discord_event_config SET
+    respond_with_credential = (SELECT s.respond_with_credential FROM saved_discord_event_links s WHERE s.event_config_id = discord_event_config.event_config_id);
+
+DROP TABLE saved_command_links;
+DROP TABLE saved_redeem_links;
+DROP TABLE saved_discord_account_links;
+DROP TABLE saved_discord_event_links;
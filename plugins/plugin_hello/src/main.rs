@@ -93,10 +93,6 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     RespPayload::GameEvent(ge) => {
                         println!("GameEvent => {}: {}", ge.name, ge.json);
                     },
-                    // Fallback if prost adds more variants in future:
-                    _ => {
-                        println!("Received unknown plugin response variant.");
-                    }
                 }
             }
         }